@@ -0,0 +1,52 @@
+//! Measures opening a large generated .docx and reading its first page with
+//! `Document::paragraphs_range` against parsing every paragraph.
+//!
+//! Run with `cargo bench --bench docx_paragraphs`. Opening only unzips the
+//! package, so `open` and `first_page` should stay flat as the document grows
+//! while `paragraphs` scales with the paragraph count.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use litchi::Document;
+use litchi::ooxml::docx::Package;
+
+const PARAGRAPH_COUNT: usize = 50_000;
+const FIRST_PAGE: std::ops::Range<usize> = 0..40;
+
+fn generate_document(path: &std::path::Path) {
+    let mut pkg = Package::new().unwrap();
+    let doc = pkg.document_mut().unwrap();
+    for index in 0..PARAGRAPH_COUNT {
+        let para = doc.add_paragraph();
+        para.add_run_with_text(&format!("Paragraph {index} of the generated body, "))
+            .bold(index % 7 == 0);
+        para.add_run_with_text("followed by a second run of plain text.");
+    }
+    pkg.save(path).unwrap();
+}
+
+fn bench_paragraphs(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("large.docx");
+    generate_document(&path);
+    let bytes = std::fs::read(&path).unwrap();
+    let doc = Document::from_bytes(bytes.clone()).unwrap();
+    assert_eq!(doc.paragraphs().unwrap().len(), PARAGRAPH_COUNT);
+    assert_eq!(
+        doc.paragraphs_range(FIRST_PAGE).unwrap().len(),
+        FIRST_PAGE.len()
+    );
+
+    let mut group = c.benchmark_group("docx_paragraphs");
+    group.sample_size(10);
+    group.bench_function("open", |b| {
+        b.iter(|| Document::from_bytes(bytes.clone()).unwrap())
+    });
+    group.bench_function("first_page", |b| {
+        b.iter(|| doc.paragraphs_range(FIRST_PAGE).unwrap().len())
+    });
+    group.bench_function("paragraphs", |b| b.iter(|| doc.paragraphs().unwrap().len()));
+    group.finish();
+}
+
+criterion_group!(benches, bench_paragraphs);
+criterion_main!(benches);
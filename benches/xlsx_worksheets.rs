@@ -0,0 +1,65 @@
+//! Compares parsing every worksheet of a large generated workbook one at a
+//! time against `Workbook::worksheets_par`.
+//!
+//! Run with `cargo bench --bench xlsx_worksheets`. On a multi-core machine the
+//! parallel variant should scale with the number of sheets until the thread
+//! pool is saturated, since shared strings and styles are parsed only once.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use litchi::ooxml::xlsx::Workbook;
+use litchi::sheet::WorkbookTrait;
+
+const SHEET_COUNT: usize = 16;
+const ROWS_PER_SHEET: u32 = 5_000;
+const COLS_PER_SHEET: u32 = 8;
+
+fn generate_workbook(path: &std::path::Path) {
+    let mut workbook = Workbook::create().unwrap();
+    for index in 0..SHEET_COUNT {
+        let sheet = if index == 0 {
+            workbook.worksheet_mut(0).unwrap()
+        } else {
+            workbook.add_worksheet(&format!("Sheet{}", index + 1))
+        };
+        for row in 1..=ROWS_PER_SHEET {
+            sheet.set_cell_value(row, 1, format!("label {}", row % 100));
+            for col in 2..=COLS_PER_SHEET {
+                sheet.set_cell_value(row, col, (row * col) as f64 + index as f64);
+            }
+        }
+    }
+    workbook.save(path).unwrap();
+}
+
+fn parse_sequential(workbook: &Workbook) -> usize {
+    let count = workbook.worksheet_count();
+    (0..count)
+        .map(|i| workbook.worksheet(i).unwrap().non_empty_cell_count())
+        .sum()
+}
+
+fn parse_parallel(workbook: &Workbook) -> usize {
+    workbook
+        .worksheets_par()
+        .unwrap()
+        .iter()
+        .map(|sheet| sheet.non_empty_cell_count())
+        .sum()
+}
+
+fn bench_worksheets(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("large.xlsx");
+    generate_workbook(&path);
+    let workbook = Workbook::open(&path).unwrap();
+    assert_eq!(parse_sequential(&workbook), parse_parallel(&workbook));
+
+    let mut group = c.benchmark_group("parse_worksheets");
+    group.sample_size(10);
+    group.bench_function("sequential", |b| b.iter(|| parse_sequential(&workbook)));
+    group.bench_function("worksheets_par", |b| b.iter(|| parse_parallel(&workbook)));
+    group.finish();
+}
+
+criterion_group!(benches, bench_worksheets);
+criterion_main!(benches);
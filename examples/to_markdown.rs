@@ -22,6 +22,11 @@
 //!     --no-metadata
 //! ```
 //!
+//! Extract pictures next to the Markdown:
+//! ```sh
+//! cargo run --example to_markdown -- input.docx -o output.md --image-dir images
+//! ```
+//!
 //! Convert to directory (auto-generates filename):
 //! ```sh
 //! cargo run --example to_markdown -- input.docx -o output_dir/
@@ -87,6 +92,13 @@ struct Args {
     #[arg(long)]
     no_metadata: bool,
 
+    /// Extract document pictures into this directory and link them
+    ///
+    /// Links use the directory as given, so it should be relative to the
+    /// location of the Markdown output.
+    #[arg(long, value_name = "DIR")]
+    image_dir: Option<PathBuf>,
+
     /// Force overwrite existing files
     #[arg(short, long)]
     force: bool,
@@ -206,13 +218,16 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Build markdown options
-    let options = MarkdownOptions::new()
+    let mut options = MarkdownOptions::new()
         .with_table_style(args.table_style.into())
         .with_formula_style(args.formula_style.into())
         .with_script_style(args.script_style.into())
         .with_strikethrough_style(args.strikethrough_style.into())
         .with_styles(!args.no_styles)
         .with_metadata(!args.no_metadata);
+    if let Some(dir) = &args.image_dir {
+        options = options.with_image_dir(dir);
+    }
 
     // Process each input file
     let mut success_count = 0;
@@ -0,0 +1,97 @@
+//! Generate an Excel workbook with a sales table and a chart drawn from it.
+//!
+//! The chart series reference the worksheet cells, so editing the numbers in
+//! Excel or LibreOffice updates the chart.
+//!
+//! Run with:
+//! ```bash
+//! cargo run --example xlsx_sales_chart -- sales.xlsx
+//! ```
+
+use litchi::ooxml::charts::axis::{Axis, CategoryAxis, ValueAxis};
+use litchi::ooxml::charts::legend::Legend;
+use litchi::ooxml::charts::plot_area::{BarTypeGroup, PlotArea, TypeGroup};
+use litchi::ooxml::charts::types::{AxisPosition, BarDirection, BarGrouping, LegendPosition};
+use litchi::ooxml::charts::{Chart, NumericData, Series, StringData, TitleText};
+use litchi::ooxml::xlsx::Workbook;
+
+type ExampleResult<T> = Result<T, Box<dyn std::error::Error + Send + Sync>>;
+
+const REGIONS: [&str; 3] = ["North", "South", "West"];
+const SALES: [(&str, [f64; 3]); 6] = [
+    ("Jan", [120.0, 98.0, 75.0]),
+    ("Feb", [135.0, 102.0, 81.0]),
+    ("Mar", [150.0, 110.0, 94.0]),
+    ("Apr", [142.0, 125.0, 99.0]),
+    ("May", [168.0, 131.0, 112.0]),
+    ("Jun", [181.0, 140.0, 127.0]),
+];
+
+fn main() -> ExampleResult<()> {
+    let output = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "sales_chart.xlsx".to_string());
+
+    let mut workbook = Workbook::create()?;
+    let sheet = workbook.worksheet_mut(0)?;
+
+    // Sales table: months in column A, one column per region
+    sheet.set_cell_value(1, 1, "Month");
+    for (col, region) in REGIONS.iter().enumerate() {
+        sheet.set_cell_value(1, col as u32 + 2, *region);
+    }
+    for (row, (month, values)) in SALES.iter().enumerate() {
+        let row = row as u32 + 2;
+        sheet.set_cell_value(row, 1, *month);
+        for (col, value) in values.iter().enumerate() {
+            sheet.set_cell_value(row, col as u32 + 2, *value);
+        }
+    }
+
+    // One series per region; references without a sheet name point at this sheet
+    let last_row = SALES.len() + 1;
+    let mut columns = BarTypeGroup::new(BarDirection::Column, BarGrouping::Clustered);
+    for (index, column) in ["B", "C", "D"].iter().enumerate() {
+        let mut series = Series::new(index as u32)
+            .with_categories(StringData::from_ref(format!("$A$2:$A${}", last_row)))
+            .with_values(NumericData::from_ref(format!(
+                "${0}$2:${0}${1}",
+                column, last_row
+            )));
+        series.title = Some(TitleText::from_ref(format!("${}$1", column)));
+        columns.common.add_series(series);
+    }
+
+    let chart = Chart::new()
+        .with_title("Monthly Sales by Region")
+        .with_legend(Legend::new(LegendPosition::Bottom))
+        .with_plot_area(
+            PlotArea::new()
+                .add_type_group(TypeGroup::Bar(columns))
+                .add_axis(Axis::Category(CategoryAxis::new(
+                    1,
+                    AxisPosition::Bottom,
+                    2,
+                )))
+                .add_axis(Axis::Value(ValueAxis::new(2, AxisPosition::Left, 1))),
+        );
+
+    // Place the chart to the right of the table
+    sheet.add_chart(chart, "F2:N20")?;
+
+    workbook.save(&output)?;
+    println!("Wrote {}", output);
+
+    // Read the chart back to show the series data stored with it
+    let workbook = Workbook::open(&output)?;
+    for chart in workbook.charts()? {
+        for series in chart.plot_area.series() {
+            if let (Some(TitleText::Literal(title)), Some(values)) = (&series.title, &series.values)
+            {
+                println!("{}: {:?}", title.text, values.values);
+            }
+        }
+    }
+
+    Ok(())
+}
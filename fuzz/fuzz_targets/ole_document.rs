@@ -0,0 +1,15 @@
+//! Fuzz the unified Document and Presentation APIs on arbitrary bytes.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use litchi::{Document, Presentation};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(doc) = Document::from_bytes(data.to_vec()) {
+        let _ = doc.text();
+    }
+    if let Ok(pres) = Presentation::from_bytes(data.to_vec()) {
+        let _ = pres.text();
+    }
+});
@@ -0,0 +1,18 @@
+//! Fuzz the compound file reader: open, list and read every stream.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use litchi::ole::OleFile;
+use std::io::Cursor;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(mut ole) = OleFile::open(Cursor::new(data)) else {
+        return;
+    };
+    for path in ole.list_streams() {
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        let _ = ole.open_stream(&path);
+    }
+    let _ = ole.list_directory_entries(&[]);
+});
@@ -0,0 +1,26 @@
+use std::env;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=LITCHI_FFI_UPDATE_HEADER");
+
+    let crate_dir = env::var("CARGO_MANIFEST_DIR").expect("CARGO_MANIFEST_DIR is set by cargo");
+    let crate_dir = Path::new(&crate_dir);
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("cbindgen.toml is valid");
+    let bindings = cbindgen::Builder::new()
+        .with_crate(crate_dir)
+        .with_config(config)
+        .generate()
+        .expect("failed to generate the C header");
+
+    // The header is generated into OUT_DIR, where a test compares it with the
+    // committed include/litchi.h; set LITCHI_FFI_UPDATE_HEADER to refresh that copy
+    let out_dir = PathBuf::from(env::var("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    bindings.write_to_file(out_dir.join("litchi.h"));
+    if env::var_os("LITCHI_FFI_UPDATE_HEADER").is_some() {
+        bindings.write_to_file(crate_dir.join("include/litchi.h"));
+    }
+}
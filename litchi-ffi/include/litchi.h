@@ -0,0 +1,170 @@
+/* litchi-ffi: C bindings for the Litchi office file parser. */
+
+#ifndef LITCHI_H
+#define LITCHI_H
+
+/* Generated by cbindgen from src/lib.rs; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Render tables as Markdown, falling back to HTML for merged cells
+#define LITCHI_TABLE_MARKDOWN 0
+
+// Render tables as HTML without styling
+#define LITCHI_TABLE_MINIMAL_HTML 1
+
+// Render tables as HTML with inline styles
+#define LITCHI_TABLE_STYLED_HTML 2
+
+// Delimit formulas with `\(` `\)` and `\[` `\]`
+#define LITCHI_FORMULA_LATEX 0
+
+// Delimit formulas with `$` and `$$`
+#define LITCHI_FORMULA_DOLLAR 1
+
+// Result of a Litchi call
+enum LitchiStatus
+#if defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+  : int32_t
+#endif // defined(__cplusplus) || __STDC_VERSION__ >= 202311L
+ {
+  // The call succeeded
+  LITCHI_STATUS_OK = 0,
+  // A required pointer argument was `NULL`
+  LITCHI_STATUS_NULL_ARGUMENT = 1,
+  // An argument had a value outside its documented range
+  LITCHI_STATUS_INVALID_ARGUMENT = 2,
+  // The data is not a recognized office file
+  LITCHI_STATUS_NOT_OFFICE_FILE = 3,
+  // The file is encrypted and cannot be read without a password
+  LITCHI_STATUS_ENCRYPTED = 4,
+  // The file or the requested conversion is not supported
+  LITCHI_STATUS_UNSUPPORTED = 5,
+  // The file is recognized but could not be parsed
+  LITCHI_STATUS_PARSE = 6,
+  // Reading the file failed
+  LITCHI_STATUS_IO = 7,
+  // Litchi panicked; the handle involved should be freed and not reused
+  LITCHI_STATUS_PANIC = 8,
+};
+#ifndef __cplusplus
+#if __STDC_VERSION__ >= 202311L
+typedef enum LitchiStatus LitchiStatus;
+#else
+typedef int32_t LitchiStatus;
+#endif // __STDC_VERSION__ >= 202311L
+#endif // __cplusplus
+
+// An opened office file
+//
+// Opaque to C; created by `litchi_open` and released by
+// `litchi_free_handle`.
+typedef struct LitchiHandle LitchiHandle;
+
+// Options for `litchi_to_markdown`
+//
+// Obtain the defaults from `litchi_markdown_options_default` and change
+// the fields you need.
+typedef struct {
+  // Whether to include text styles such as bold and italic
+  bool include_styles;
+  // Whether to start with a block of document metadata
+  bool include_metadata;
+  // Whether to render footnotes and endnotes as `[^1]` references
+  bool include_footnotes;
+  // Whether to render section headers and footers around the content
+  bool include_headers_footers;
+  // Whether to render text boxes after their anchor paragraphs
+  bool include_floating_text;
+  // One of the `LITCHI_TABLE_*` constants
+  uint32_t table_style;
+  // One of the `LITCHI_FORMULA_*` constants
+  uint32_t formula_style;
+} LitchiMarkdownOptions;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Open an office file from memory, detecting its format from the contents
+//
+// On success stores a new handle in `*out_handle`; on failure stores
+// `NULL` there (when `out_handle` is not `NULL`) and returns the error.
+// `data` is only read during the call.
+//
+// # Safety
+//
+// `data` must point to `len` readable bytes and `out_handle` must be
+// `NULL` or point to writable storage for a pointer.
+LitchiStatus litchi_open(const uint8_t *data, size_t len, LitchiHandle **out_handle);
+
+// Extract all text of a document, presentation or workbook
+//
+// Returns a string to release with `litchi_free_string`, or `NULL` on
+// failure.
+//
+// # Safety
+//
+// `handle` must be `NULL` or a live handle from `litchi_open`.
+char *litchi_text(const LitchiHandle *handle);
+
+// Fill in the default Markdown options
+LitchiMarkdownOptions litchi_markdown_options_default(void);
+
+// Convert a document or presentation to Markdown
+//
+// `options` may be `NULL` for the defaults. Workbooks have no Markdown
+// conversion and fail with `LITCHI_STATUS_UNSUPPORTED`. Returns a string
+// to release with `litchi_free_string`, or `NULL` on failure.
+//
+// # Safety
+//
+// `handle` must be `NULL` or a live handle from `litchi_open`, and
+// `options` must be `NULL` or point to a valid `LitchiMarkdownOptions`.
+char *litchi_to_markdown(const LitchiHandle *handle, const LitchiMarkdownOptions *options);
+
+// Read the document properties as a JSON object
+//
+// Absent properties are left out and dates are RFC 3339 strings, for
+// example `{"title":"Report","created":"2024-05-01T09:30:00Z"}`. Returns a
+// string to release with `litchi_free_string`, or `NULL` on failure.
+//
+// # Safety
+//
+// `handle` must be `NULL` or a live handle from `litchi_open`.
+char *litchi_metadata_json(const LitchiHandle *handle);
+
+// Release a handle from `litchi_open`; `NULL` is ignored
+//
+// # Safety
+//
+// `handle` must be `NULL` or a handle from `litchi_open` that has not been
+// freed yet.
+void litchi_free_handle(LitchiHandle *handle);
+
+// Release a string returned by Litchi; `NULL` is ignored
+//
+// # Safety
+//
+// `text` must be `NULL` or a string returned by a Litchi function that has
+// not been freed yet.
+void litchi_free_string(char *text);
+
+// Status of the last failed call on this thread, or `LITCHI_STATUS_OK`
+LitchiStatus litchi_last_error_code(void);
+
+// Description of the last failed call on this thread, or `NULL`
+//
+// The string is owned by Litchi and stays valid until the next Litchi
+// call on this thread; do not free it.
+const char *litchi_last_error_message(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LITCHI_H */
@@ -0,0 +1,589 @@
+//! C bindings for Litchi
+//!
+//! A stable C ABI over the format-agnostic [`litchi::open_from_bytes`] entry
+//! point: open a file from memory, extract its text, convert it to Markdown
+//! or read its metadata as JSON. The header `include/litchi.h` is generated
+//! from this file by `cbindgen` during the build.
+//!
+//! # Memory ownership
+//!
+//! - Input buffers are borrowed for the duration of the call only; Litchi
+//!   copies what it keeps.
+//! - A handle returned through `litchi_open` is owned by the caller and must
+//!   be released exactly once with `litchi_free_handle`. It may be moved to
+//!   another thread but must not be used from two threads at the same time.
+//! - Every `char *` returned by a function is a NUL-terminated UTF-8 string
+//!   owned by the caller and must be released with `litchi_free_string`.
+//!   Interior NUL characters in the extracted text are dropped.
+//! - The string returned by `litchi_last_error_message` is owned by Litchi
+//!   and stays valid until the next Litchi call on the same thread.
+//!
+//! # Errors
+//!
+//! Functions that return a `LitchiStatus` report failures through it;
+//! functions that return a pointer return `NULL` instead. In both cases the
+//! status and a description are kept per thread and can be read back with
+//! `litchi_last_error_code` and `litchi_last_error_message`. Every call
+//! except the `litchi_free_*` functions clears the previous error first.
+//! Panics are caught at the boundary and reported as
+//! `LITCHI_STATUS_PANIC`; they never unwind into the caller.
+//!
+//! # Examples
+//!
+//! ```c
+//! #include "litchi.h"
+//!
+//! LitchiHandle *handle = NULL;
+//! if (litchi_open(data, len, &handle) != LITCHI_STATUS_OK) {
+//!     fprintf(stderr, "%s\n", litchi_last_error_message());
+//!     return 1;
+//! }
+//! char *text = litchi_text(handle);
+//! puts(text);
+//! litchi_free_string(text);
+//! litchi_free_handle(handle);
+//! ```
+
+use litchi::markdown::{FormulaStyle, MarkdownOptions, TableStyle, ToMarkdown};
+use litchi::{Error, OfficeFile, TextExtract};
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{CString, c_char};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+/// Render tables as Markdown, falling back to HTML for merged cells
+pub const LITCHI_TABLE_MARKDOWN: u32 = 0;
+/// Render tables as HTML without styling
+pub const LITCHI_TABLE_MINIMAL_HTML: u32 = 1;
+/// Render tables as HTML with inline styles
+pub const LITCHI_TABLE_STYLED_HTML: u32 = 2;
+
+/// Delimit formulas with `\(` `\)` and `\[` `\]`
+pub const LITCHI_FORMULA_LATEX: u32 = 0;
+/// Delimit formulas with `$` and `$$`
+pub const LITCHI_FORMULA_DOLLAR: u32 = 1;
+
+/// Result of a Litchi call
+#[repr(i32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LitchiStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was `NULL`
+    NullArgument = 1,
+    /// An argument had a value outside its documented range
+    InvalidArgument = 2,
+    /// The data is not a recognized office file
+    NotOfficeFile = 3,
+    /// The file is encrypted and cannot be read without a password
+    Encrypted = 4,
+    /// The file or the requested conversion is not supported
+    Unsupported = 5,
+    /// The file is recognized but could not be parsed
+    Parse = 6,
+    /// Reading the file failed
+    Io = 7,
+    /// Litchi panicked; the handle involved should be freed and not reused
+    Panic = 8,
+}
+
+/// An opened office file
+///
+/// Opaque to C; created by `litchi_open` and released by
+/// `litchi_free_handle`.
+pub struct LitchiHandle {
+    file: OfficeFile,
+}
+
+/// Options for `litchi_to_markdown`
+///
+/// Obtain the defaults from `litchi_markdown_options_default` and change
+/// the fields you need.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct LitchiMarkdownOptions {
+    /// Whether to include text styles such as bold and italic
+    pub include_styles: bool,
+    /// Whether to start with a block of document metadata
+    pub include_metadata: bool,
+    /// Whether to render footnotes and endnotes as `[^1]` references
+    pub include_footnotes: bool,
+    /// Whether to render section headers and footers around the content
+    pub include_headers_footers: bool,
+    /// Whether to render text boxes after their anchor paragraphs
+    pub include_floating_text: bool,
+    /// One of the `LITCHI_TABLE_*` constants
+    pub table_style: u32,
+    /// One of the `LITCHI_FORMULA_*` constants
+    pub formula_style: u32,
+}
+
+impl Default for LitchiMarkdownOptions {
+    fn default() -> Self {
+        let options = MarkdownOptions::default();
+        Self {
+            include_styles: options.include_styles,
+            include_metadata: options.include_metadata,
+            include_footnotes: options.include_footnotes,
+            include_headers_footers: options.include_headers_footers,
+            include_floating_text: options.include_floating_text,
+            table_style: match options.table_style {
+                TableStyle::Markdown => LITCHI_TABLE_MARKDOWN,
+                TableStyle::MinimalHtml => LITCHI_TABLE_MINIMAL_HTML,
+                TableStyle::StyledHtml => LITCHI_TABLE_STYLED_HTML,
+            },
+            formula_style: match options.formula_style {
+                FormulaStyle::LaTeX => LITCHI_FORMULA_LATEX,
+                FormulaStyle::Dollar => LITCHI_FORMULA_DOLLAR,
+            },
+        }
+    }
+}
+
+impl LitchiMarkdownOptions {
+    fn to_options(self) -> Result<MarkdownOptions, FfiError> {
+        let table_style = match self.table_style {
+            LITCHI_TABLE_MARKDOWN => TableStyle::Markdown,
+            LITCHI_TABLE_MINIMAL_HTML => TableStyle::MinimalHtml,
+            LITCHI_TABLE_STYLED_HTML => TableStyle::StyledHtml,
+            other => return Err(FfiError::invalid(format!("unknown table style {other}"))),
+        };
+        let formula_style = match self.formula_style {
+            LITCHI_FORMULA_LATEX => FormulaStyle::LaTeX,
+            LITCHI_FORMULA_DOLLAR => FormulaStyle::Dollar,
+            other => return Err(FfiError::invalid(format!("unknown formula style {other}"))),
+        };
+        Ok(MarkdownOptions::new()
+            .with_styles(self.include_styles)
+            .with_metadata(self.include_metadata)
+            .with_footnotes(self.include_footnotes)
+            .with_headers_footers(self.include_headers_footers)
+            .with_floating_text(self.include_floating_text)
+            .with_table_style(table_style)
+            .with_formula_style(formula_style))
+    }
+}
+
+/// A failure to report through the last-error slot
+struct FfiError {
+    status: LitchiStatus,
+    message: String,
+}
+
+impl FfiError {
+    fn null(argument: &str) -> Self {
+        Self {
+            status: LitchiStatus::NullArgument,
+            message: format!("`{argument}` must not be NULL"),
+        }
+    }
+
+    fn invalid(message: String) -> Self {
+        Self {
+            status: LitchiStatus::InvalidArgument,
+            message,
+        }
+    }
+}
+
+impl From<Error> for FfiError {
+    fn from(error: Error) -> Self {
+        let status = match &error {
+            Error::Io(_) => LitchiStatus::Io,
+            Error::NotOfficeFile => LitchiStatus::NotOfficeFile,
+            Error::Encrypted(_) | Error::IncorrectPassword(_) | Error::UnsupportedEncryption(_) => {
+                LitchiStatus::Encrypted
+            },
+            Error::UnsupportedFeature(_) | Error::FeatureDisabled(_) => LitchiStatus::Unsupported,
+            _ => LitchiStatus::Parse,
+        };
+        Self {
+            status,
+            message: error.to_string(),
+        }
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<(LitchiStatus, CString)>> = const { RefCell::new(None) };
+}
+
+fn set_last_error(status: LitchiStatus, message: String) {
+    let message = into_c_string(message);
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some((status, message)));
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    let detail = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown cause");
+    format!("litchi panicked: {detail}")
+}
+
+/// Run the body of an exported function, recording any error or panic.
+fn guard<T>(body: impl FnOnce() -> Result<T, FfiError>) -> Result<T, LitchiStatus> {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+    let error = match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(value)) => return Ok(value),
+        Ok(Err(error)) => error,
+        Err(payload) => FfiError {
+            status: LitchiStatus::Panic,
+            message: panic_message(payload.as_ref()),
+        },
+    };
+    set_last_error(error.status, error.message);
+    Err(error.status)
+}
+
+/// Convert a Rust string into a C string, dropping interior NULs.
+fn into_c_string(text: String) -> CString {
+    CString::new(text).unwrap_or_else(|error| {
+        let mut bytes = error.into_vec();
+        bytes.retain(|&byte| byte != 0);
+        CString::new(bytes).unwrap_or_default()
+    })
+}
+
+/// Borrow the file behind a handle argument.
+///
+/// # Safety
+///
+/// `handle` must be `NULL` or a live pointer from `litchi_open`.
+unsafe fn file<'a>(handle: *const LitchiHandle) -> Result<&'a OfficeFile, FfiError> {
+    // SAFETY: the caller guarantees the pointer is NULL or live.
+    unsafe { handle.as_ref() }
+        .map(|handle| &handle.file)
+        .ok_or_else(|| FfiError::null("handle"))
+}
+
+/// Run a string-producing call and hand its result to the caller.
+fn string_result(body: impl FnOnce() -> Result<String, FfiError>) -> *mut c_char {
+    guard(|| body().map(|text| into_c_string(text).into_raw())).unwrap_or(ptr::null_mut())
+}
+
+/// Open an office file from memory, detecting its format from the contents
+///
+/// On success stores a new handle in `*out_handle`; on failure stores
+/// `NULL` there (when `out_handle` is not `NULL`) and returns the error.
+/// `data` is only read during the call.
+///
+/// # Safety
+///
+/// `data` must point to `len` readable bytes and `out_handle` must be
+/// `NULL` or point to writable storage for a pointer.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn litchi_open(
+    data: *const u8,
+    len: usize,
+    out_handle: *mut *mut LitchiHandle,
+) -> LitchiStatus {
+    let result = guard(|| {
+        // SAFETY: the caller guarantees `out_handle` is NULL or writable.
+        let out_handle =
+            unsafe { out_handle.as_mut() }.ok_or_else(|| FfiError::null("out_handle"))?;
+        *out_handle = ptr::null_mut();
+        if data.is_null() {
+            return Err(FfiError::null("data"));
+        }
+
+        // SAFETY: the caller guarantees `data` points to `len` readable bytes.
+        let bytes = unsafe { std::slice::from_raw_parts(data, len) }.to_vec();
+        let file = litchi::open_from_bytes(bytes)?;
+        *out_handle = Box::into_raw(Box::new(LitchiHandle { file }));
+        Ok(())
+    });
+    result.err().unwrap_or(LitchiStatus::Ok)
+}
+
+/// Extract all text of a document, presentation or workbook
+///
+/// Returns a string to release with `litchi_free_string`, or `NULL` on
+/// failure.
+///
+/// # Safety
+///
+/// `handle` must be `NULL` or a live handle from `litchi_open`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn litchi_text(handle: *const LitchiHandle) -> *mut c_char {
+    string_result(|| {
+        // SAFETY: forwarded from the caller.
+        let file = unsafe { file(handle) }?;
+        Ok(file.text()?)
+    })
+}
+
+/// Fill in the default Markdown options
+#[unsafe(no_mangle)]
+pub extern "C" fn litchi_markdown_options_default() -> LitchiMarkdownOptions {
+    LitchiMarkdownOptions::default()
+}
+
+/// Convert a document or presentation to Markdown
+///
+/// `options` may be `NULL` for the defaults. Workbooks have no Markdown
+/// conversion and fail with `LITCHI_STATUS_UNSUPPORTED`. Returns a string
+/// to release with `litchi_free_string`, or `NULL` on failure.
+///
+/// # Safety
+///
+/// `handle` must be `NULL` or a live handle from `litchi_open`, and
+/// `options` must be `NULL` or point to a valid `LitchiMarkdownOptions`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn litchi_to_markdown(
+    handle: *const LitchiHandle,
+    options: *const LitchiMarkdownOptions,
+) -> *mut c_char {
+    string_result(|| {
+        // SAFETY: forwarded from the caller.
+        let file = unsafe { file(handle) }?;
+        // SAFETY: the caller guarantees `options` is NULL or valid.
+        let options = match unsafe { options.as_ref() } {
+            Some(options) => options.to_options()?,
+            None => MarkdownOptions::default(),
+        };
+        match file {
+            OfficeFile::Document(doc) => Ok(doc.to_markdown_with_options(&options)?),
+            OfficeFile::Presentation(pres) => Ok(pres.to_markdown_with_options(&options)?),
+            OfficeFile::Spreadsheet(_) => Err(FfiError {
+                status: LitchiStatus::Unsupported,
+                message: "spreadsheets cannot be converted to Markdown".to_string(),
+            }),
+        }
+    })
+}
+
+/// Read the document properties as a JSON object
+///
+/// Absent properties are left out and dates are RFC 3339 strings, for
+/// example `{"title":"Report","created":"2024-05-01T09:30:00Z"}`. Returns a
+/// string to release with `litchi_free_string`, or `NULL` on failure.
+///
+/// # Safety
+///
+/// `handle` must be `NULL` or a live handle from `litchi_open`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn litchi_metadata_json(handle: *const LitchiHandle) -> *mut c_char {
+    string_result(|| {
+        // SAFETY: forwarded from the caller.
+        let file = unsafe { file(handle) }?;
+        let metadata = file.metadata()?;
+        serde_json::to_string(&metadata).map_err(|error| FfiError {
+            status: LitchiStatus::Parse,
+            message: format!("failed to serialize metadata: {error}"),
+        })
+    })
+}
+
+/// Release a handle from `litchi_open`; `NULL` is ignored
+///
+/// # Safety
+///
+/// `handle` must be `NULL` or a handle from `litchi_open` that has not been
+/// freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn litchi_free_handle(handle: *mut LitchiHandle) {
+    if !handle.is_null() {
+        // SAFETY: the caller guarantees the handle came from `Box::into_raw`
+        // in `litchi_open` and is freed only once.
+        let handle = unsafe { Box::from_raw(handle) };
+        // A panicking destructor leaks the rest of the file instead of
+        // unwinding into C.
+        let _ = panic::catch_unwind(AssertUnwindSafe(|| drop(handle)));
+    }
+}
+
+/// Release a string returned by Litchi; `NULL` is ignored
+///
+/// # Safety
+///
+/// `text` must be `NULL` or a string returned by a Litchi function that has
+/// not been freed yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn litchi_free_string(text: *mut c_char) {
+    if !text.is_null() {
+        // SAFETY: the caller guarantees the string came from
+        // `CString::into_raw` and is freed only once.
+        drop(unsafe { CString::from_raw(text) });
+    }
+}
+
+/// Status of the last failed call on this thread, or `LITCHI_STATUS_OK`
+#[unsafe(no_mangle)]
+pub extern "C" fn litchi_last_error_code() -> LitchiStatus {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(LitchiStatus::Ok, |(status, _)| *status)
+    })
+}
+
+/// Description of the last failed call on this thread, or `NULL`
+///
+/// The string is owned by Litchi and stays valid until the next Litchi
+/// call on this thread; do not free it.
+#[unsafe(no_mangle)]
+pub extern "C" fn litchi_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |(_, message)| message.as_ptr())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::CStr;
+
+    fn open(path: &str) -> Result<*mut LitchiHandle, LitchiStatus> {
+        let data = std::fs::read(format!(
+            "{}/../test-data/{path}",
+            env!("CARGO_MANIFEST_DIR")
+        ))
+        .expect("fixture exists");
+        let mut handle = ptr::null_mut();
+        match unsafe { litchi_open(data.as_ptr(), data.len(), &mut handle) } {
+            LitchiStatus::Ok => Ok(handle),
+            status => {
+                assert!(handle.is_null());
+                Err(status)
+            },
+        }
+    }
+
+    fn take_string(text: *mut c_char) -> Option<String> {
+        if text.is_null() {
+            return None;
+        }
+        let result = unsafe { CStr::from_ptr(text) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { litchi_free_string(text) };
+        Some(result)
+    }
+
+    fn last_error_message() -> String {
+        let message = litchi_last_error_message();
+        assert!(!message.is_null());
+        unsafe { CStr::from_ptr(message) }
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_handle_is_send() {
+        fn assert_send<T: Send>() {}
+        assert_send::<LitchiHandle>();
+    }
+
+    #[test]
+    fn test_open_text_metadata() {
+        let handle = open("ooxml/docx/documentProperties.docx").unwrap();
+        assert_eq!(litchi_last_error_code(), LitchiStatus::Ok);
+        assert!(litchi_last_error_message().is_null());
+
+        let text = take_string(unsafe { litchi_text(handle) }).unwrap();
+        assert!(!text.is_empty());
+
+        let json = take_string(unsafe { litchi_metadata_json(handle) }).unwrap();
+        let metadata: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(metadata["title"], "Hello World");
+        assert_eq!(metadata["author"], "Paolo Mottadelli");
+
+        unsafe { litchi_free_handle(handle) };
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let handle = open("ooxml/docx/documentProperties.docx").unwrap();
+        let mut options = litchi_markdown_options_default();
+        options.include_metadata = true;
+        let markdown = take_string(unsafe { litchi_to_markdown(handle, &options) }).unwrap();
+        assert!(markdown.contains("Hello World"));
+        assert!(take_string(unsafe { litchi_to_markdown(handle, ptr::null()) }).is_some());
+
+        options.table_style = 7;
+        assert!(take_string(unsafe { litchi_to_markdown(handle, &options) }).is_none());
+        assert_eq!(litchi_last_error_code(), LitchiStatus::InvalidArgument);
+        assert_eq!(last_error_message(), "unknown table style 7");
+        unsafe { litchi_free_handle(handle) };
+
+        let handle = open("ooxml/xlsx/CustomXMLMappings.xlsx").unwrap();
+        assert!(take_string(unsafe { litchi_to_markdown(handle, ptr::null()) }).is_none());
+        assert_eq!(litchi_last_error_code(), LitchiStatus::Unsupported);
+        assert!(take_string(unsafe { litchi_text(handle) }).is_some());
+        assert_eq!(litchi_last_error_code(), LitchiStatus::Ok);
+        unsafe { litchi_free_handle(handle) };
+    }
+
+    #[test]
+    fn test_open_errors() {
+        let mut handle = ptr::null_mut();
+        let status = unsafe { litchi_open(ptr::null(), 0, &mut handle) };
+        assert_eq!(status, LitchiStatus::NullArgument);
+        assert_eq!(last_error_message(), "`data` must not be NULL");
+
+        let data = b"not an office file";
+        let status = unsafe { litchi_open(data.as_ptr(), data.len(), ptr::null_mut()) };
+        assert_eq!(status, LitchiStatus::NullArgument);
+
+        let status = unsafe { litchi_open(data.as_ptr(), data.len(), &mut handle) };
+        assert_eq!(status, LitchiStatus::NotOfficeFile);
+        assert_eq!(litchi_last_error_code(), LitchiStatus::NotOfficeFile);
+        assert!(handle.is_null());
+
+        assert_eq!(open("odf/encrypted_aes.odt"), Err(LitchiStatus::Encrypted));
+    }
+
+    #[test]
+    fn test_null_handle() {
+        assert!(unsafe { litchi_text(ptr::null()) }.is_null());
+        assert_eq!(litchi_last_error_code(), LitchiStatus::NullArgument);
+        assert!(unsafe { litchi_metadata_json(ptr::null()) }.is_null());
+        assert_eq!(last_error_message(), "`handle` must not be NULL");
+
+        // Freeing NULL is a no-op and keeps the last error.
+        unsafe {
+            litchi_free_handle(ptr::null_mut());
+            litchi_free_string(ptr::null_mut());
+        }
+        assert_eq!(litchi_last_error_code(), LitchiStatus::NullArgument);
+    }
+
+    #[test]
+    fn test_panic_is_caught() {
+        let result: Result<(), _> = guard(|| panic!("boom"));
+        assert_eq!(result, Err(LitchiStatus::Panic));
+        assert_eq!(last_error_message(), "litchi panicked: boom");
+    }
+
+    #[test]
+    fn test_error_status() {
+        let status = |error| FfiError::from(error).status;
+        assert_eq!(status(Error::NotOfficeFile), LitchiStatus::NotOfficeFile);
+        assert_eq!(
+            status(Error::InvalidFormat("bad header".to_string())),
+            LitchiStatus::Parse
+        );
+    }
+
+    #[test]
+    fn test_header_is_up_to_date() {
+        // Regenerate it with `LITCHI_FFI_UPDATE_HEADER=1 cargo build`
+        assert_eq!(
+            include_str!(concat!(env!("OUT_DIR"), "/litchi.h")),
+            include_str!("../include/litchi.h"),
+            "include/litchi.h is stale"
+        );
+    }
+
+    #[test]
+    fn test_interior_nul_is_dropped() {
+        assert_eq!(into_c_string("a\0b".to_string()).as_bytes(), b"ab");
+    }
+}
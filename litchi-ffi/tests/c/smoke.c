@@ -0,0 +1,129 @@
+/*
+ * Smoke test for the litchi-ffi C ABI.
+ *
+ * Usage: smoke <office file>...
+ *
+ * Opens each file, prints the size of its text, Markdown and metadata, and
+ * checks that invalid input is reported through the error functions.
+ * Exits with a non-zero status on the first failure.
+ */
+
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+#include "litchi.h"
+
+static unsigned char *read_file(const char *path, size_t *len) {
+    FILE *file = fopen(path, "rb");
+    if (file == NULL) {
+        return NULL;
+    }
+    fseek(file, 0, SEEK_END);
+    long size = ftell(file);
+    fseek(file, 0, SEEK_SET);
+
+    unsigned char *data = malloc(size > 0 ? (size_t)size : 1);
+    if (data != NULL && fread(data, 1, (size_t)size, file) != (size_t)size) {
+        free(data);
+        data = NULL;
+    }
+    fclose(file);
+    *len = (size_t)size;
+    return data;
+}
+
+static int fail(const char *path, const char *what) {
+    const char *message = litchi_last_error_message();
+    fprintf(stderr, "%s: %s failed (status %d): %s\n", path, what, (int)litchi_last_error_code(),
+            message != NULL ? message : "no message");
+    return 1;
+}
+
+static int check_file(const char *path) {
+    size_t len = 0;
+    unsigned char *data = read_file(path, &len);
+    if (data == NULL) {
+        fprintf(stderr, "%s: cannot read file\n", path);
+        return 1;
+    }
+
+    LitchiHandle *handle = NULL;
+    LitchiStatus status = litchi_open(data, len, &handle);
+    /* The input buffer is only borrowed during the call. */
+    free(data);
+    if (status != LITCHI_STATUS_OK) {
+        return fail(path, "litchi_open");
+    }
+
+    char *text = litchi_text(handle);
+    if (text == NULL) {
+        litchi_free_handle(handle);
+        return fail(path, "litchi_text");
+    }
+
+    char *metadata = litchi_metadata_json(handle);
+    if (metadata == NULL || metadata[0] != '{') {
+        litchi_free_string(text);
+        litchi_free_handle(handle);
+        return fail(path, "litchi_metadata_json");
+    }
+
+    LitchiMarkdownOptions options = litchi_markdown_options_default();
+    options.table_style = LITCHI_TABLE_MINIMAL_HTML;
+    char *markdown = litchi_to_markdown(handle, &options);
+    if (markdown == NULL && litchi_last_error_code() != LITCHI_STATUS_UNSUPPORTED) {
+        litchi_free_string(metadata);
+        litchi_free_string(text);
+        litchi_free_handle(handle);
+        return fail(path, "litchi_to_markdown");
+    }
+
+    printf("%s: %zu bytes of text, %zu bytes of Markdown, metadata %s\n", path, strlen(text),
+           markdown != NULL ? strlen(markdown) : 0, metadata);
+
+    litchi_free_string(markdown);
+    litchi_free_string(metadata);
+    litchi_free_string(text);
+    litchi_free_handle(handle);
+    return 0;
+}
+
+static int check_errors(void) {
+    static const unsigned char garbage[] = "not an office file";
+    LitchiHandle *handle = (LitchiHandle *)&handle;
+
+    if (litchi_open(garbage, sizeof garbage, &handle) != LITCHI_STATUS_NOT_OFFICE_FILE ||
+        handle != NULL || litchi_last_error_message() == NULL) {
+        fprintf(stderr, "garbage input was not rejected\n");
+        return 1;
+    }
+    if (litchi_open(NULL, 0, &handle) != LITCHI_STATUS_NULL_ARGUMENT) {
+        fprintf(stderr, "NULL data was not rejected\n");
+        return 1;
+    }
+    if (litchi_text(NULL) != NULL || litchi_last_error_code() != LITCHI_STATUS_NULL_ARGUMENT) {
+        fprintf(stderr, "NULL handle was not rejected\n");
+        return 1;
+    }
+
+    litchi_free_handle(NULL);
+    litchi_free_string(NULL);
+    return 0;
+}
+
+int main(int argc, char **argv) {
+    if (argc < 2) {
+        fprintf(stderr, "usage: %s <office file>...\n", argv[0]);
+        return 2;
+    }
+    if (check_errors() != 0) {
+        return 1;
+    }
+    for (int i = 1; i < argc; i++) {
+        if (check_file(argv[i]) != 0) {
+            return 1;
+        }
+    }
+    return 0;
+}
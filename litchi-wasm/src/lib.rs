@@ -0,0 +1,66 @@
+//! WebAssembly bindings for Litchi
+//!
+//! A small `wasm-bindgen` wrapper that exposes text extraction, Markdown
+//! conversion and formula conversion to JavaScript. Files are passed in as
+//! bytes (for example from a `File` or `fetch` response) and the format is
+//! detected from their contents.
+//!
+//! # Examples
+//!
+//! ```javascript
+//! import init, { extractText, toMarkdown } from "./pkg/litchi_wasm.js";
+//!
+//! await init();
+//! const data = new Uint8Array(await file.arrayBuffer());
+//! console.log(extractText(data));
+//! console.log(toMarkdown(data));
+//! ```
+
+use litchi::markdown::ToMarkdown;
+use litchi::{OfficeFile, TextExtract};
+use wasm_bindgen::prelude::*;
+
+/// Detect the format of a file from its contents
+///
+/// Returns the lowercase file extension (such as `"docx"` or `"xls"`), or
+/// `undefined` if the data is not a recognized office file.
+#[wasm_bindgen(js_name = detectFormat)]
+pub fn detect_format(data: &[u8]) -> Option<String> {
+    litchi::detect_file_format_from_bytes(data).map(|format| format!("{format:?}").to_lowercase())
+}
+
+/// Extract all text from a document, presentation or workbook
+///
+/// Throws if the data is not a supported office file or cannot be parsed.
+#[wasm_bindgen(js_name = extractText)]
+pub fn extract_text(data: &[u8]) -> Result<String, JsError> {
+    let file = litchi::open_from_bytes(data.to_vec())?;
+    Ok(file.text()?)
+}
+
+/// Convert a document or presentation to Markdown
+///
+/// Throws for workbooks, which have no Markdown conversion, and for data
+/// that cannot be parsed.
+#[wasm_bindgen(js_name = toMarkdown)]
+pub fn to_markdown(data: &[u8]) -> Result<String, JsError> {
+    match litchi::open_from_bytes(data.to_vec())? {
+        OfficeFile::Document(doc) => Ok(doc.to_markdown()?),
+        OfficeFile::Presentation(pres) => Ok(pres.to_markdown()?),
+        OfficeFile::Spreadsheet(_) => {
+            Err(JsError::new("spreadsheets cannot be converted to Markdown"))
+        },
+    }
+}
+
+/// Convert an Office Math (OMML) fragment to LaTeX
+#[wasm_bindgen(js_name = ommlToLatex)]
+pub fn omml_to_latex(omml: &str) -> Result<String, JsError> {
+    Ok(litchi::formula::omml_to_latex(omml)?)
+}
+
+/// Convert MathType (MTEF) equation data to LaTeX
+#[wasm_bindgen(js_name = mtefToLatex)]
+pub fn mtef_to_latex(data: &[u8]) -> Result<String, JsError> {
+    Ok(litchi::formula::mtef_to_latex(data)?)
+}
@@ -4,8 +4,9 @@ Litchi - High-performance Office file format parser
 Type stubs for the litchi_py Python extension module.
 """
 
+from datetime import datetime
 from pathlib import Path
-from typing import Optional, List
+from typing import Optional, List, Tuple, Union
 from enum import Enum
 
 class FileFormat(Enum):
@@ -126,6 +127,20 @@ def detect_file_format_from_bytes(data: bytes) -> Optional[FileFormat]:
     """
     ...
 
+# Exceptions
+
+class LitchiError(Exception):
+    """Base class for errors raised by litchi_py"""
+
+class LitchiParseError(LitchiError):
+    """The file is malformed or could not be parsed"""
+
+class LitchiEncryptedError(LitchiError):
+    """The file is password-protected"""
+
+class LitchiUnsupportedError(LitchiError):
+    """The file format or a feature it uses is not supported"""
+
 # Document API
 
 class Run:
@@ -244,7 +259,28 @@ class Document:
         
         Raises:
             IOError: If the file cannot be read
-            ValueError: If the file format is invalid or unsupported
+            LitchiParseError: If the file is malformed
+            LitchiEncryptedError: If the file is password-protected
+            LitchiUnsupportedError: If the file is not a supported document
+        """
+        ...
+    
+    @staticmethod
+    def from_bytes(data: bytes) -> Document:
+        """Load a Word document from bytes
+        
+        The file format (.doc or .docx) is automatically detected.
+        
+        Args:
+            data: Contents of the document file
+        
+        Returns:
+            Document instance
+        
+        Raises:
+            LitchiParseError: If the file is malformed
+            LitchiEncryptedError: If the file is password-protected
+            LitchiUnsupportedError: If the file is not a supported document
         """
         ...
     
@@ -312,7 +348,28 @@ class Presentation:
         
         Raises:
             IOError: If the file cannot be read
-            ValueError: If the file format is invalid or unsupported
+            LitchiParseError: If the file is malformed
+            LitchiEncryptedError: If the file is password-protected
+            LitchiUnsupportedError: If the file is not a supported presentation
+        """
+        ...
+    
+    @staticmethod
+    def from_bytes(data: bytes) -> Presentation:
+        """Load a PowerPoint presentation from bytes
+        
+        The file format (.ppt or .pptx) is automatically detected.
+        
+        Args:
+            data: Contents of the presentation file
+        
+        Returns:
+            Presentation instance
+        
+        Raises:
+            LitchiParseError: If the file is malformed
+            LitchiEncryptedError: If the file is password-protected
+            LitchiUnsupportedError: If the file is not a supported presentation
         """
         ...
     
@@ -338,13 +395,85 @@ class Presentation:
 
 # Sheet API
 
+CellValue = Union[None, bool, int, float, str, datetime]
+"""A cell value converted to a Python-native object"""
+
 class Worksheet:
     """A worksheet in a workbook
     
-    Note: This is a placeholder for future worksheet-level API.
-    Currently, use Workbook.worksheet_names() and Workbook.text() for data access.
+    Cell values are returned as Python-native objects: None for empty cells,
+    bool, int, float, str, and datetime.datetime for dates. Error cells give
+    their error text (e.g. "#DIV/0!"). Formula cells give their cached result,
+    or the computed result when evaluate=True is passed.
+    
+    Rows and columns are 1-based, as in Excel.
+    
+    Apple Numbers workbooks do not support cell access.
+    
+    Example:
+        >>> from litchi_py import Workbook
+        >>> sheet = Workbook.open("workbook.xlsx").sheet("Sheet1")
+        >>> print(sheet.cell(1, 1))
+        >>> print(sheet.cell(2, 3, evaluate=True))
+        >>> csv = sheet.to_csv()
     """
-    pass
+    
+    def name(self) -> str:
+        """Get the worksheet name"""
+        ...
+    
+    def dimensions(self) -> Optional[Tuple[int, int, int, int]]:
+        """Get the used range of the worksheet
+        
+        Returns:
+            Tuple of (min_row, min_col, max_row, max_col), 1-based, or None
+            if the worksheet is empty
+        """
+        ...
+    
+    def cell(self, row: int, col: int, evaluate: bool = False) -> CellValue:
+        """Get the value of a cell
+        
+        Args:
+            row: Row number (1-based)
+            col: Column number (1-based)
+            evaluate: Compute formulas with the formula evaluator instead of
+                using their cached results
+        
+        Returns:
+            The cell value as a Python object, or None for empty cells
+        
+        Raises:
+            IndexError: If row or col is 0
+        """
+        ...
+    
+    def rows(self, evaluate: bool = False) -> List[List[CellValue]]:
+        """Get the values of all rows in the used range
+        
+        Args:
+            evaluate: Compute formulas with the formula evaluator instead of
+                using their cached results
+        
+        Returns:
+            List of rows, each a list of cell values as Python objects
+        """
+        ...
+    
+    def to_csv(self, evaluate: bool = False) -> str:
+        """Export the used range as CSV
+        
+        Dates are written as "YYYY-MM-DD HH:MM:SS" and formulas as their
+        results. Formulas without a cached result are written as "=FORMULA".
+        
+        Args:
+            evaluate: Compute formulas with the formula evaluator instead of
+                using their cached results
+        
+        Returns:
+            CSV text with one line per row
+        """
+        ...
 
 class Workbook:
     """Excel workbook interface
@@ -357,10 +486,10 @@ class Workbook:
         >>> from litchi_py import Workbook
         >>> wb = Workbook.open("workbook.xlsx")
         >>> print(f"Worksheets: {wb.worksheet_count()}")
-        >>> for name in wb.worksheet_names():
-        ...     print(f"Sheet: {name}")
-        >>> text = wb.text()
-        >>> print(text)
+        >>> for name in wb.sheet_names():
+        ...     print(f"{name}: {wb.sheet(name).cell(1, 1)}")
+        >>> for row in wb.sheet(0).rows():
+        ...     print(row)
     """
     
     @staticmethod
@@ -377,7 +506,28 @@ class Workbook:
         
         Raises:
             IOError: If the file cannot be read
-            ValueError: If the file format is invalid or unsupported
+            LitchiParseError: If the file is malformed
+            LitchiEncryptedError: If the file is password-protected
+            LitchiUnsupportedError: If the file is not a supported workbook
+        """
+        ...
+    
+    @staticmethod
+    def from_bytes(data: bytes) -> Workbook:
+        """Load an Excel workbook from bytes
+        
+        The file format (.xls, .xlsx, .xlsb, .ods, .numbers) is automatically detected.
+        
+        Args:
+            data: Contents of the workbook file
+        
+        Returns:
+            Workbook instance
+        
+        Raises:
+            LitchiParseError: If the file is malformed
+            LitchiEncryptedError: If the file is password-protected
+            LitchiUnsupportedError: If the file is not a supported workbook
         """
         ...
     
@@ -393,6 +543,50 @@ class Workbook:
         """
         ...
     
+    def sheet_names(self) -> List[str]:
+        """Get all worksheet names
+        
+        Alias of worksheet_names().
+        
+        Returns:
+            List of worksheet names
+        """
+        ...
+    
+    def is_1904_date_system(self) -> bool:
+        """Check whether the workbook uses the 1904 date system
+        
+        Date cells are converted to datetime.datetime using this setting.
+        
+        Returns:
+            True for the 1904 date system, False for the 1900 date system
+        """
+        ...
+    
+    def sheet(self, key: Union[int, str]) -> Worksheet:
+        """Get a worksheet by index or name
+        
+        Args:
+            key: 0-based worksheet index, or worksheet name
+        
+        Returns:
+            Worksheet instance
+        
+        Raises:
+            IndexError: If the index is out of range
+            KeyError: If no worksheet has the given name
+            TypeError: If the key is neither an int nor a str
+        """
+        ...
+    
+    def sheets(self) -> List[Worksheet]:
+        """Get all worksheets
+        
+        Returns:
+            List of Worksheet instances, in workbook order
+        """
+        ...
+    
     def text(self) -> str:
         """Extract all text from all worksheets
         
@@ -402,6 +596,10 @@ class Workbook:
         ...
 
 __all__ = [
+    "LitchiError",
+    "LitchiParseError",
+    "LitchiEncryptedError",
+    "LitchiUnsupportedError",
     "FileFormat",
     "RGBColor",
     "Length",
@@ -417,5 +615,6 @@ __all__ = [
     "Slide",
     "Workbook",
     "Worksheet",
+    "CellValue",
 ]
 
@@ -1,15 +1,50 @@
 //! Common types and utilities
 
-use pyo3::exceptions::{PyException, PyIOError, PyValueError};
+use pyo3::exceptions::{PyException, PyIOError};
 use pyo3::prelude::*;
 use pyo3::types::PyModule;
 use std::path::PathBuf;
 
+pyo3::create_exception!(
+    litchi_py,
+    LitchiError,
+    PyException,
+    "Base class for errors raised by litchi_py"
+);
+pyo3::create_exception!(
+    litchi_py,
+    LitchiParseError,
+    LitchiError,
+    "The file is malformed or could not be parsed"
+);
+pyo3::create_exception!(
+    litchi_py,
+    LitchiEncryptedError,
+    LitchiError,
+    "The file is password-protected"
+);
+pyo3::create_exception!(
+    litchi_py,
+    LitchiUnsupportedError,
+    LitchiError,
+    "The file format or a feature it uses is not supported"
+);
+
 /// Registers common types with the Python module
 pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<FileFormat>()?;
     m.add_class::<RGBColor>()?;
     m.add_class::<Length>()?;
+    m.add("LitchiError", m.py().get_type::<LitchiError>())?;
+    m.add("LitchiParseError", m.py().get_type::<LitchiParseError>())?;
+    m.add(
+        "LitchiEncryptedError",
+        m.py().get_type::<LitchiEncryptedError>(),
+    )?;
+    m.add(
+        "LitchiUnsupportedError",
+        m.py().get_type::<LitchiUnsupportedError>(),
+    )?;
     m.add_function(wrap_pyfunction!(detect_file_format, m)?)?;
     m.add_function(wrap_pyfunction!(detect_file_format_from_bytes, m)?)?;
     Ok(())
@@ -17,17 +52,67 @@ pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
 /// Converts a Rust litchi::Error to a Python exception
 pub fn to_py_err(err: litchi::Error) -> PyErr {
+    use litchi::Error;
+
+    let msg = err.to_string();
     match err {
-        litchi::Error::Io(e) => PyIOError::new_err(e.to_string()),
-        litchi::Error::InvalidFormat(msg) => PyValueError::new_err(msg),
-        litchi::Error::Unsupported(msg) => PyValueError::new_err(msg),
-        _ => PyException::new_err(err.to_string()),
+        Error::Io(_) => PyIOError::new_err(msg),
+        Error::Encrypted(_) | Error::IncorrectPassword(_) => LitchiEncryptedError::new_err(msg),
+        Error::NotOfficeFile
+        | Error::UnsupportedFeature(_)
+        | Error::UnsupportedEncryption(_)
+        | Error::FeatureDisabled(_) => LitchiUnsupportedError::new_err(msg),
+        Error::ParseError(_)
+        | Error::Binary(_)
+        | Error::InvalidFormat(_)
+        | Error::CorruptedFile(_)
+        | Error::MissingPart(_)
+        | Error::Xml { .. }
+        | Error::InvalidContentType { .. }
+        | Error::Zip(_) => LitchiParseError::new_err(msg),
+        _ => LitchiError::new_err(msg),
     }
 }
 
 /// Converts a boxed error to a Python exception
-pub fn boxed_err_to_py_err(err: Box<dyn std::error::Error>) -> PyErr {
-    PyException::new_err(err.to_string())
+///
+/// litchi errors keep their specific exception class.
+pub fn boxed_err_to_py_err(err: Box<dyn std::error::Error + Send + Sync>) -> PyErr {
+    let err = match err.downcast::<litchi::Error>() {
+        Ok(err) => return to_py_err(*err),
+        Err(err) => err,
+    };
+    match err.downcast::<std::io::Error>() {
+        Ok(err) => PyIOError::new_err(err.to_string()),
+        Err(err) => LitchiError::new_err(err.to_string()),
+    }
+}
+
+/// Wrapper that moves a borrow of non-`Sync` data into `Python::detach`
+struct DetachRef<'a, T: ?Sized>(&'a T);
+
+// SAFETY: `detach` runs its closure on the calling thread, and the
+// `unsendable` classes holding this data can only be used from the thread
+// that created them, so the data is never accessed from two threads.
+unsafe impl<T: ?Sized> Send for DetachRef<'_, T> {}
+
+impl<'a, T: ?Sized> DetachRef<'a, T> {
+    fn into_inner(self) -> &'a T {
+        self.0
+    }
+}
+
+/// Runs `f` on `value` with the GIL released
+///
+/// Used for parsing and text extraction so other Python threads, such as
+/// an asyncio event loop, keep running. `value` need not be `Sync`.
+pub fn detach_with<T: ?Sized, R: Send>(
+    py: Python<'_>,
+    value: &T,
+    f: impl FnOnce(&T) -> R + Send,
+) -> R {
+    let value = DetachRef(value);
+    py.detach(move || f(value.into_inner()))
 }
 
 /// File format enumeration
@@ -5,7 +5,7 @@ use pyo3::types::PyModule;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::common::to_py_err;
+use crate::common::{detach_with, to_py_err};
 
 /// Registers document types with the Python module
 pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -58,10 +58,38 @@ impl Document {
     ///
     /// Raises:
     ///     IOError: If the file cannot be read
-    ///     ValueError: If the file format is invalid or unsupported
+    ///     LitchiParseError: If the file is malformed
+    ///     LitchiEncryptedError: If the file is password-protected
+    ///     LitchiUnsupportedError: If the file is not a supported document
     #[staticmethod]
-    fn open(path: PathBuf) -> PyResult<Self> {
-        let doc = litchi::Document::open(path).map_err(to_py_err)?;
+    fn open(py: Python<'_>, path: PathBuf) -> PyResult<Self> {
+        let doc = py
+            .detach(|| litchi::Document::open(path))
+            .map_err(to_py_err)?;
+        Ok(Document {
+            inner: Arc::new(doc),
+        })
+    }
+
+    /// Load a Word document from bytes
+    ///
+    /// The file format (.doc or .docx) is automatically detected.
+    ///
+    /// Args:
+    ///     data: Contents of the document file
+    ///
+    /// Returns:
+    ///     Document instance
+    ///
+    /// Raises:
+    ///     LitchiParseError: If the file is malformed
+    ///     LitchiEncryptedError: If the file is password-protected
+    ///     LitchiUnsupportedError: If the file is not a supported document
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &[u8]) -> PyResult<Self> {
+        let doc = py
+            .detach(|| litchi::Document::from_bytes(data.to_vec()))
+            .map_err(to_py_err)?;
         Ok(Document {
             inner: Arc::new(doc),
         })
@@ -71,8 +99,8 @@ impl Document {
     ///
     /// Returns:
     ///     All text content as a single string
-    fn text(&self) -> PyResult<String> {
-        self.inner.text().map_err(to_py_err)
+    fn text(&self, py: Python<'_>) -> PyResult<String> {
+        detach_with(py, &*self.inner, |doc| doc.text()).map_err(to_py_err)
     }
 
     /// Get all paragraphs in the document
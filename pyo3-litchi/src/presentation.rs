@@ -5,7 +5,7 @@ use pyo3::types::PyModule;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::common::to_py_err;
+use crate::common::{detach_with, to_py_err};
 
 /// Registers presentation types with the Python module
 pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -57,10 +57,38 @@ impl Presentation {
     ///
     /// Raises:
     ///     IOError: If the file cannot be read
-    ///     ValueError: If the file format is invalid or unsupported
+    ///     LitchiParseError: If the file is malformed
+    ///     LitchiEncryptedError: If the file is password-protected
+    ///     LitchiUnsupportedError: If the file is not a supported presentation
     #[staticmethod]
-    fn open(path: PathBuf) -> PyResult<Self> {
-        let pres = litchi::Presentation::open(path).map_err(to_py_err)?;
+    fn open(py: Python<'_>, path: PathBuf) -> PyResult<Self> {
+        let pres = py
+            .detach(|| litchi::Presentation::open(path))
+            .map_err(to_py_err)?;
+        Ok(Presentation {
+            inner: Arc::new(pres),
+        })
+    }
+
+    /// Load a PowerPoint presentation from bytes
+    ///
+    /// The file format (.ppt or .pptx) is automatically detected.
+    ///
+    /// Args:
+    ///     data: Contents of the presentation file
+    ///
+    /// Returns:
+    ///     Presentation instance
+    ///
+    /// Raises:
+    ///     LitchiParseError: If the file is malformed
+    ///     LitchiEncryptedError: If the file is password-protected
+    ///     LitchiUnsupportedError: If the file is not a supported presentation
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &[u8]) -> PyResult<Self> {
+        let pres = py
+            .detach(|| litchi::Presentation::from_bytes(data.to_vec()))
+            .map_err(to_py_err)?;
         Ok(Presentation {
             inner: Arc::new(pres),
         })
@@ -70,8 +98,8 @@ impl Presentation {
     ///
     /// Returns:
     ///     All text content from all slides as a single string
-    fn text(&self) -> PyResult<String> {
-        self.inner.text().map_err(to_py_err)
+    fn text(&self, py: Python<'_>) -> PyResult<String> {
+        detach_with(py, &*self.inner, |pres| pres.text()).map_err(to_py_err)
     }
 
     /// Get the number of slides in the presentation
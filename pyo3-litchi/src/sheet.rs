@@ -1,11 +1,16 @@
 //! Sheet/Workbook API bindings
 
+use litchi::sheet::text::formats::{DelimitedConfig, write_delimited};
+use litchi::sheet::{CellValue, FormulaEvaluator, WorkbookTrait};
+use pyo3::IntoPyObjectExt;
+use pyo3::exceptions::{PyIndexError, PyKeyError, PyTypeError};
 use pyo3::prelude::*;
 use pyo3::types::PyModule;
+use std::future::Future;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use crate::common::boxed_err_to_py_err;
+use crate::common::{boxed_err_to_py_err, detach_with};
 
 /// Registers sheet types with the Python module
 pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
@@ -14,6 +19,9 @@ pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
     Ok(())
 }
 
+/// A cell value and whether the cell's number format shows a date
+type SheetCell = (CellValue, bool);
+
 /// Excel workbook interface
 ///
 /// Provides support for Excel workbooks in various formats (.xls, .xlsx, .xlsb).
@@ -27,12 +35,14 @@ pub fn register(m: &Bound<'_, PyModule>) -> PyResult<()> {
 /// # Open an Excel workbook
 /// wb = Workbook.open("workbook.xlsx")
 ///
-/// # Get worksheet count
-/// print(f"Worksheets: {wb.worksheet_count()}")
+/// # Access worksheets by name or index
+/// for name in wb.sheet_names():
+///     sheet = wb.sheet(name)
+///     print(f"{name}: {sheet.cell(1, 1)}")
 ///
-/// # Access worksheet names
-/// for name in wb.worksheet_names():
-///     print(f"Sheet: {name}")
+/// # Read values as Python objects
+/// for row in wb.sheet(0).rows():
+///     print(row)
 /// ```
 #[pyclass(unsendable)]
 pub struct Workbook {
@@ -53,10 +63,38 @@ impl Workbook {
     ///
     /// Raises:
     ///     IOError: If the file cannot be read
-    ///     ValueError: If the file format is invalid or unsupported
+    ///     LitchiParseError: If the file is malformed
+    ///     LitchiEncryptedError: If the file is password-protected
+    ///     LitchiUnsupportedError: If the file is not a supported workbook
     #[staticmethod]
-    fn open(path: PathBuf) -> PyResult<Self> {
-        let wb = litchi::sheet::Workbook::open(path).map_err(boxed_err_to_py_err)?;
+    fn open(py: Python<'_>, path: PathBuf) -> PyResult<Self> {
+        let wb = py
+            .detach(|| litchi::sheet::Workbook::open(path))
+            .map_err(boxed_err_to_py_err)?;
+        Ok(Workbook {
+            inner: Arc::new(wb),
+        })
+    }
+
+    /// Load an Excel workbook from bytes
+    ///
+    /// The file format (.xls, .xlsx, .xlsb, .ods, .numbers) is automatically detected.
+    ///
+    /// Args:
+    ///     data: Contents of the workbook file
+    ///
+    /// Returns:
+    ///     Workbook instance
+    ///
+    /// Raises:
+    ///     LitchiParseError: If the file is malformed
+    ///     LitchiEncryptedError: If the file is password-protected
+    ///     LitchiUnsupportedError: If the file is not a supported workbook
+    #[staticmethod]
+    fn from_bytes(py: Python<'_>, data: &[u8]) -> PyResult<Self> {
+        let wb = py
+            .detach(|| litchi::sheet::Workbook::from_bytes(data.to_vec()))
+            .map_err(boxed_err_to_py_err)?;
         Ok(Workbook {
             inner: Arc::new(wb),
         })
@@ -78,12 +116,88 @@ impl Workbook {
         self.inner.worksheet_names().map_err(boxed_err_to_py_err)
     }
 
+    /// Get all worksheet names
+    ///
+    /// Alias of `worksheet_names()`.
+    ///
+    /// Returns:
+    ///     List of worksheet names
+    fn sheet_names(&self) -> PyResult<Vec<String>> {
+        self.worksheet_names()
+    }
+
+    /// Check whether the workbook uses the 1904 date system
+    ///
+    /// Date cells are converted to `datetime.datetime` using this setting.
+    ///
+    /// Returns:
+    ///     True for the 1904 date system, False for the 1900 date system
+    fn is_1904_date_system(&self) -> bool {
+        self.inner.is_1904_date_system()
+    }
+
+    /// Get a worksheet by index or name
+    ///
+    /// Args:
+    ///     key: 0-based worksheet index, or worksheet name
+    ///
+    /// Returns:
+    ///     Worksheet instance
+    ///
+    /// Raises:
+    ///     IndexError: If the index is out of range
+    ///     KeyError: If no worksheet has the given name
+    ///     TypeError: If the key is neither an int nor a str
+    fn sheet(&self, key: &Bound<'_, PyAny>) -> PyResult<Worksheet> {
+        let names = self.worksheet_names()?;
+        let index = if let Ok(index) = key.extract::<isize>() {
+            usize::try_from(index)
+                .ok()
+                .filter(|&index| index < names.len())
+                .ok_or_else(|| {
+                    PyIndexError::new_err(format!("Worksheet index {} out of range", index))
+                })?
+        } else if let Ok(name) = key.extract::<String>() {
+            names
+                .iter()
+                .position(|sheet| *sheet == name)
+                .ok_or_else(|| PyKeyError::new_err(format!("Worksheet '{}' not found", name)))?
+        } else {
+            return Err(PyTypeError::new_err(
+                "Worksheet key must be an int or a str",
+            ));
+        };
+
+        Ok(Worksheet {
+            workbook: Arc::clone(&self.inner),
+            index,
+            name: names[index].clone(),
+        })
+    }
+
+    /// Get all worksheets
+    ///
+    /// Returns:
+    ///     List of Worksheet instances, in workbook order
+    fn sheets(&self) -> PyResult<Vec<Worksheet>> {
+        Ok(self
+            .worksheet_names()?
+            .into_iter()
+            .enumerate()
+            .map(|(index, name)| Worksheet {
+                workbook: Arc::clone(&self.inner),
+                index,
+                name,
+            })
+            .collect())
+    }
+
     /// Extract all text from all worksheets
     ///
     /// Returns:
     ///     All text content as a single string
-    fn text(&self) -> PyResult<String> {
-        self.inner.text().map_err(boxed_err_to_py_err)
+    fn text(&self, py: Python<'_>) -> PyResult<String> {
+        detach_with(py, &*self.inner, |wb| wb.text()).map_err(boxed_err_to_py_err)
     }
 
     fn __repr__(&self) -> PyResult<String> {
@@ -94,16 +208,243 @@ impl Workbook {
 
 /// A worksheet in a workbook
 ///
-/// Note: This is a placeholder for future worksheet-level API.
-/// Currently, use Workbook.worksheet_names() and Workbook.text() for data access.
-#[pyclass]
+/// Cell values are returned as Python-native objects: `None` for empty cells,
+/// `bool`, `int`, `float`, `str`, and `datetime.datetime` for dates. Error
+/// cells give their error text (e.g. "#DIV/0!"). Formula cells give their
+/// cached result, or the computed result when `evaluate=True` is passed.
+///
+/// Rows and columns are 1-based, as in Excel.
+///
+/// Apple Numbers workbooks do not support cell access.
+///
+/// # Examples
+///
+/// ```python
+/// from litchi_py import Workbook
+///
+/// sheet = Workbook.open("workbook.xlsx").sheet("Sheet1")
+/// print(sheet.cell(1, 1))
+/// print(sheet.cell(2, 3, evaluate=True))
+/// csv = sheet.to_csv()
+/// ```
+#[pyclass(unsendable)]
 pub struct Worksheet {
-    _private: (),
+    workbook: Arc<litchi::sheet::Workbook>,
+    index: usize,
+    name: String,
 }
 
 #[pymethods]
 impl Worksheet {
+    /// Get the worksheet name
+    ///
+    /// Returns:
+    ///     Worksheet name
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Get the used range of the worksheet
+    ///
+    /// Returns:
+    ///     Tuple of (min_row, min_col, max_row, max_col), 1-based, or None
+    ///     if the worksheet is empty
+    fn dimensions(&self) -> PyResult<Option<(u32, u32, u32, u32)>> {
+        self.with_sheet(|_, sheet| Ok(sheet.dimensions()))
+    }
+
+    /// Get the value of a cell
+    ///
+    /// Args:
+    ///     row: Row number (1-based)
+    ///     col: Column number (1-based)
+    ///     evaluate: Compute formulas with the formula evaluator instead of
+    ///         using their cached results
+    ///
+    /// Returns:
+    ///     The cell value as a Python object, or None for empty cells
+    ///
+    /// Raises:
+    ///     IndexError: If row or col is 0
+    #[pyo3(signature = (row, col, evaluate = false))]
+    fn cell(&self, py: Python<'_>, row: u32, col: u32, evaluate: bool) -> PyResult<Py<PyAny>> {
+        if row == 0 || col == 0 {
+            return Err(PyIndexError::new_err("Rows and columns are 1-based"));
+        }
+
+        let (value, is_date) = self.with_sheet(|wb, sheet| {
+            let value = if evaluate {
+                block_on(FormulaEvaluator::new(wb).evaluate_cell(sheet.name(), row, col))??
+            } else {
+                sheet.cell_value(row, col)?.into_owned()
+            };
+            Ok((value, sheet.is_date_formatted(row, col)))
+        })?;
+        cell_to_py(py, &value, is_date, self.workbook.is_1904_date_system())
+    }
+
+    /// Get the values of all rows in the used range
+    ///
+    /// Args:
+    ///     evaluate: Compute formulas with the formula evaluator instead of
+    ///         using their cached results
+    ///
+    /// Returns:
+    ///     List of rows, each a list of cell values as Python objects
+    #[pyo3(signature = (evaluate = false))]
+    fn rows(&self, py: Python<'_>, evaluate: bool) -> PyResult<Vec<Vec<Py<PyAny>>>> {
+        let is_1904 = self.workbook.is_1904_date_system();
+        self.read_rows(evaluate)?
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|(value, is_date)| cell_to_py(py, value, *is_date, is_1904))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Export the used range as CSV
+    ///
+    /// Dates are written as "YYYY-MM-DD HH:MM:SS" and formulas as their
+    /// results. Formulas without a cached result are written as "=FORMULA".
+    ///
+    /// Args:
+    ///     evaluate: Compute formulas with the formula evaluator instead of
+    ///         using their cached results
+    ///
+    /// Returns:
+    ///     CSV text with one line per row
+    #[pyo3(signature = (evaluate = false))]
+    fn to_csv(&self, evaluate: bool) -> PyResult<String> {
+        let is_1904 = self.workbook.is_1904_date_system();
+        let data: Vec<Vec<CellValue>> = self
+            .read_rows(evaluate)?
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|(value, is_date)| csv_value(value, is_date, is_1904))
+                    .collect()
+            })
+            .collect();
+
+        let mut out = Vec::new();
+        write_delimited(&data, &mut out, DelimitedConfig::csv().with_write_bom(None))
+            .map_err(boxed_err_to_py_err)?;
+        Ok(String::from_utf8_lossy(&out).into_owned())
+    }
+
     fn __repr__(&self) -> String {
-        "<Worksheet>".to_string()
+        format!("<Worksheet: '{}'>", self.name)
+    }
+}
+
+impl Worksheet {
+    /// Run `f` with the workbook and this worksheet.
+    fn with_sheet<R>(
+        &self,
+        f: impl FnOnce(&dyn WorkbookTrait, &dyn litchi::sheet::Worksheet) -> litchi::sheet::Result<R>,
+    ) -> PyResult<R> {
+        self.workbook
+            .with_workbook_trait(|wb| {
+                let sheet = wb.worksheet_by_index(self.index)?;
+                f(wb, sheet.as_ref())
+            })
+            .map_err(boxed_err_to_py_err)
+    }
+
+    /// Read the used range row by row.
+    fn read_rows(&self, evaluate: bool) -> PyResult<Vec<Vec<SheetCell>>> {
+        self.with_sheet(|wb, sheet| {
+            let Some((min_row, min_col, max_row, max_col)) = sheet.dimensions() else {
+                return Ok(Vec::new());
+            };
+
+            let values = if evaluate {
+                block_on(FormulaEvaluator::new(wb).evaluate_sheet(sheet.name()))??
+            } else {
+                (min_row..=max_row)
+                    .map(|row| {
+                        (min_col..=max_col)
+                            .map(|col| Ok(sheet.cell_value(row, col)?.into_owned()))
+                            .collect()
+                    })
+                    .collect::<litchi::sheet::Result<Vec<Vec<_>>>>()?
+            };
+
+            Ok(values
+                .into_iter()
+                .zip(min_row..)
+                .map(|(row_values, row)| {
+                    row_values
+                        .into_iter()
+                        .zip(min_col..)
+                        .map(|(value, col)| (value, sheet.is_date_formatted(row, col)))
+                        .collect()
+                })
+                .collect())
+        })
+    }
+}
+
+/// Run a formula evaluator future to completion.
+fn block_on<F: Future>(future: F) -> litchi::sheet::Result<F::Output> {
+    let runtime = tokio::runtime::Builder::new_current_thread().build()?;
+    Ok(runtime.block_on(future))
+}
+
+/// Get the date serial of a date cell.
+///
+/// Numbers count as dates when the cell's number format shows a date.
+fn date_serial(value: &CellValue, is_date: bool) -> Option<f64> {
+    match *value {
+        CellValue::DateTime(serial) => Some(serial),
+        CellValue::Int(n) if is_date => Some(n as f64),
+        CellValue::Float(f) if is_date => Some(f),
+        _ => None,
+    }
+}
+
+/// Convert a cell value to a Python-native object.
+fn cell_to_py(
+    py: Python<'_>,
+    value: &CellValue,
+    is_date: bool,
+    is_1904: bool,
+) -> PyResult<Py<PyAny>> {
+    if let Some(datetime) = date_serial(value, is_date)
+        .and_then(|serial| CellValue::DateTime(serial).as_datetime(is_1904))
+    {
+        return datetime.into_py_any(py);
+    }
+
+    match value {
+        CellValue::Empty => Ok(py.None()),
+        CellValue::Bool(b) => b.into_py_any(py),
+        CellValue::Int(n) => n.into_py_any(py),
+        // Serials without a calendar date stay numbers
+        CellValue::Float(f) | CellValue::DateTime(f) => f.into_py_any(py),
+        CellValue::String(s) | CellValue::Error(s) => s.into_py_any(py),
+        CellValue::Formula { cached_value, .. } => match cached_value {
+            Some(cached) => cell_to_py(py, cached, is_date, is_1904),
+            None => Ok(py.None()),
+        },
+    }
+}
+
+/// Get the value a cell shows in CSV output.
+fn csv_value(value: CellValue, is_date: bool, is_1904: bool) -> CellValue {
+    if let Some(datetime) = date_serial(&value, is_date)
+        .and_then(|serial| CellValue::DateTime(serial).as_datetime(is_1904))
+    {
+        return CellValue::String(datetime.to_string());
+    }
+
+    match value {
+        CellValue::Formula {
+            cached_value: Some(cached),
+            ..
+        } => csv_value(*cached, is_date, is_1904),
+        value => value,
     }
 }
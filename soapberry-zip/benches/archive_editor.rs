@@ -0,0 +1,86 @@
+//! Compares saving an edited archive by rewriting every entry against
+//! copying the untouched entries with `ArchiveEditor`.
+//!
+//! Run with `cargo bench --bench archive_editor`. The editor's time should
+//! track the size of the modified entry, while a full rewrite stays roughly
+//! constant because it recompresses the whole archive.
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use soapberry_zip::office::{ArchiveEditor, ArchiveReader, StreamingArchiveWriter};
+
+const PART_COUNT: usize = 32;
+const PART_SIZE: usize = 256 * 1024;
+
+fn xml_part(index: usize, size: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(size + 32);
+    let mut row = 0usize;
+    while data.len() < size {
+        let line = format!(
+            "<row r=\"{row}\"><c t=\"n\"><v>{}</v></c></row>",
+            row * 31 + index
+        );
+        data.extend_from_slice(line.as_bytes());
+        row += 1;
+    }
+    data.truncate(size);
+    data
+}
+
+fn source_archive() -> Vec<u8> {
+    let mut writer = StreamingArchiveWriter::new();
+    for i in 0..PART_COUNT {
+        writer
+            .write_deflated(
+                &format!("xl/worksheets/sheet{i}.xml"),
+                &xml_part(i, PART_SIZE),
+            )
+            .unwrap();
+    }
+    writer.finish_to_bytes().unwrap()
+}
+
+fn full_rewrite(source: &[u8], name: &str, data: &[u8]) -> Vec<u8> {
+    let reader = ArchiveReader::new(source).unwrap();
+    let mut writer = StreamingArchiveWriter::new();
+    for i in 0..PART_COUNT {
+        let part = format!("xl/worksheets/sheet{i}.xml");
+        if part == name {
+            writer.write_deflated(&part, data).unwrap();
+        } else {
+            writer
+                .write_deflated(&part, &reader.read(&part).unwrap())
+                .unwrap();
+        }
+    }
+    writer.finish_to_bytes().unwrap()
+}
+
+fn edit(source: &[u8], name: &str, data: &[u8]) -> Vec<u8> {
+    let mut editor = ArchiveEditor::from_slice(source).unwrap();
+    editor.write_deflated(name, data);
+    editor.finish_to_bytes().unwrap()
+}
+
+fn bench_save(c: &mut Criterion) {
+    let source = source_archive();
+    let mut group = c.benchmark_group("save_modified_part");
+    group.sample_size(10);
+
+    for size in [4 * 1024, 64 * 1024, 1024 * 1024] {
+        let data = xml_part(PART_COUNT, size);
+        group.throughput(Throughput::Bytes(size as u64));
+        group.bench_with_input(BenchmarkId::new("full_rewrite", size), &data, |b, data| {
+            b.iter(|| full_rewrite(&source, "xl/worksheets/sheet0.xml", data))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("archive_editor", size),
+            &data,
+            |b, data| b.iter(|| edit(&source, "xl/worksheets/sheet0.xml", data)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_save);
+criterion_main!(benches);
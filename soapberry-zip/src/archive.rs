@@ -264,6 +264,16 @@ pub struct ZipSliceVerifier<D> {
 }
 
 impl<D> ZipSliceVerifier<D> {
+    /// Wrap `reader`, checking its output against `verifier` once finished.
+    pub(crate) fn new(reader: D, verifier: ZipVerification) -> Self {
+        ZipSliceVerifier {
+            reader,
+            verifier,
+            crc: 0,
+            size: 0,
+        }
+    }
+
     /// Consumes the `ZipSliceVerifier`, returning the underlying reader.
     pub fn into_inner(self) -> D {
         self.reader
@@ -1661,6 +1671,30 @@ impl<'a> ZipFileHeaderRecord<'a> {
     pub fn extra_fields(&self) -> ExtraFields<'_> {
         ExtraFields::new(self.extra_field)
     }
+
+    /// The general purpose bit flags of this record.
+    #[inline]
+    pub(crate) fn flags(&self) -> u16 {
+        self.flags
+    }
+
+    /// The raw "version made by" and "version needed to extract" fields.
+    #[inline]
+    pub(crate) fn versions(&self) -> (u16, u16) {
+        (self.version_made_by, self.version_needed)
+    }
+
+    /// The raw MS-DOS modification time and date fields.
+    #[inline]
+    pub(crate) fn dos_timestamp(&self) -> (u16, u16) {
+        (self.last_mod_time, self.last_mod_date)
+    }
+
+    /// The raw external file attributes.
+    #[inline]
+    pub(crate) fn external_file_attrs(&self) -> u32 {
+        self.external_file_attrs
+    }
 }
 
 /// Contains directions to where the Zip entry's data is located within the Zip archive.
@@ -1709,7 +1743,7 @@ pub(crate) struct ZipLocalFileHeaderFixed {
 }
 
 impl ZipLocalFileHeaderFixed {
-    const SIZE: usize = 30;
+    pub(crate) const SIZE: usize = 30;
     pub const SIGNATURE: u32 = 0x04034b50;
 
     pub fn parse(data: &[u8]) -> Result<ZipLocalFileHeaderFixed, Error> {
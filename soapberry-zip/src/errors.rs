@@ -87,6 +87,9 @@ pub enum ErrorKind {
     /// File not found in archive
     FileNotFound(String),
 
+    /// File changed since the archive was opened
+    FileChanged(String),
+
     /// Unsupported compression method
     UnsupportedCompressionMethod(u16),
 }
@@ -146,6 +149,9 @@ impl std::fmt::Display for ErrorKind {
             ErrorKind::FileNotFound(ref name) => {
                 write!(f, "File not found in archive: {}", name)
             },
+            ErrorKind::FileChanged(ref name) => {
+                write!(f, "File changed since the archive was opened: {}", name)
+            },
             ErrorKind::UnsupportedCompressionMethod(method) => {
                 write!(f, "Unsupported compression method: {}", method)
             },
@@ -34,6 +34,7 @@ mod mode;
 pub mod office;
 pub mod path;
 mod reader_at;
+mod recovery;
 pub mod time;
 mod utils;
 mod writer;
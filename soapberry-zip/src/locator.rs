@@ -10,7 +10,7 @@ use std::fs::File;
 use std::io::Seek;
 use std::num::NonZeroU64;
 
-const END_OF_CENTRAL_DIR_SIGNAUTRE: u32 = 0x06054b50;
+pub(crate) const END_OF_CENTRAL_DIR_SIGNAUTRE: u32 = 0x06054b50;
 pub(crate) const END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES: [u8; 4] =
     END_OF_CENTRAL_DIR_SIGNAUTRE.to_le_bytes();
 
@@ -118,7 +118,11 @@ impl ZipLocator {
         }
 
         let zip64l =
-            &data[location.saturating_sub(Zip64EndOfCentralDirectoryLocatorRecord::SIZE)..];
+            &data[location.saturating_sub(Zip64EndOfCentralDirectoryLocatorRecord::SIZE)..location];
+        if !Zip64EndOfCentralDirectoryLocatorRecord::is_present(zip64l) {
+            return EndOfCentralDirectory::create(eocd);
+        }
+
         let zip64_locator = Zip64EndOfCentralDirectoryLocatorRecord::parse(zip64l)?;
         let zip64_eocd = &data[(zip64_locator.directory_offset as usize).min(data.len())..];
         let zip64_record = Zip64EndOfCentralDirectoryRecord::parse(zip64_eocd)?;
@@ -399,10 +403,10 @@ impl ZipLocator {
         // eocd or don't have enough data in the buffer
         let eocd64l_pos = if reader.is_marked() || eocd64l_size > buffer_pos {
             if (eocd64l_size as u64) > eocd_offset {
-                return Err((
-                    reader.inner,
-                    Error::from(ErrorKind::MissingZip64EndOfCentralDirectory),
-                ));
+                return match EndOfCentralDirectory::create(eocd) {
+                    Ok(eocd) => Ok((reader.inner, eocd)),
+                    Err(e) => Err((reader.inner, e)),
+                };
             }
 
             let read = reader.read_exact_at(
@@ -419,6 +423,13 @@ impl ZipLocator {
         };
 
         let zip64l_eocd = &buffer[eocd64l_pos..eocd64l_pos + eocd64l_size];
+        if !Zip64EndOfCentralDirectoryLocatorRecord::is_present(zip64l_eocd) {
+            return match EndOfCentralDirectory::create(eocd) {
+                Ok(eocd) => Ok((reader.inner, eocd)),
+                Err(e) => Err((reader.inner, e)),
+            };
+        }
+
         let zip64_locator = match Zip64EndOfCentralDirectoryLocatorRecord::parse(zip64l_eocd) {
             Ok(locator) => locator,
             Err(e) => return Err((reader.inner, e)),
@@ -677,7 +688,9 @@ impl EndOfCentralDirectoryRecordFixed {
 
     pub fn is_zip64(&self) -> bool {
         // https://github.com/zlib-ng/minizip-ng/blob/55db144e03027b43263e5ebcb599bf0878ba58de/mz_zip.c#L1011
-        self.num_entries == u16::MAX || // 4.4.22
+        self.num_entries == u16::MAX || // 4.4.21
+        self.total_entries == u16::MAX || // 4.4.22
+        self.central_dir_size == u32::MAX || // 4.4.23
         self.central_dir_offset == u32::MAX // 4.4.24
     }
 }
@@ -704,6 +717,16 @@ struct Zip64EndOfCentralDirectoryLocatorRecord {
 impl Zip64EndOfCentralDirectoryLocatorRecord {
     const SIZE: usize = 20;
 
+    /// Whether the data directly preceding the end of central directory starts
+    /// with a zip64 locator.
+    ///
+    /// Saturated end of central directory fields without a locator are taken
+    /// at face value, as some archivers write them for exactly 65535 entries
+    /// without switching to zip64.
+    fn is_present(data: &[u8]) -> bool {
+        data.len() >= Self::SIZE && le_u32(&data[0..4]) == END_OF_CENTRAL_DIR_LOCATOR_SIGNATURE
+    }
+
     pub fn parse(data: &[u8]) -> Result<Zip64EndOfCentralDirectoryLocatorRecord, Error> {
         if data.len() < Self::SIZE {
             return Err(Error::from(ErrorKind::Eof));
@@ -957,4 +980,158 @@ mod tests {
             );
         }
     }
+
+    /// Builds a single entry archive where every size and offset is stored in
+    /// ZIP64 records, and the given EOCD fields are saturated to point at them.
+    fn forged_zip64_archive(data: &[u8], saturated: &[usize]) -> Vec<u8> {
+        let name = b"xl/worksheets/sheet1.xml";
+        let crc = crate::crc32(data);
+        let size = data.len() as u64;
+        let mut out = Vec::new();
+
+        // Local file header with both sizes in the ZIP64 extra field
+        out.extend_from_slice(&crate::ZipLocalFileHeaderFixed::SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&45u16.to_le_bytes());
+        out.extend_from_slice(&[0u8; 8]); // flags, method, time, date
+        out.extend_from_slice(&crc.to_le_bytes());
+        out.extend_from_slice(&u32::MAX.to_le_bytes());
+        out.extend_from_slice(&u32::MAX.to_le_bytes());
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&20u16.to_le_bytes());
+        out.extend_from_slice(name);
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&16u16.to_le_bytes());
+        out.extend_from_slice(&size.to_le_bytes());
+        out.extend_from_slice(&size.to_le_bytes());
+        out.extend_from_slice(data);
+
+        // Central directory header with sizes and offset in the extra field
+        let cd_offset = out.len() as u64;
+        out.extend_from_slice(&crate::CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&45u16.to_le_bytes());
+        out.extend_from_slice(&45u16.to_le_bytes());
+        out.extend_from_slice(&[0u8; 8]); // flags, method, time, date
+        out.extend_from_slice(&crc.to_le_bytes());
+        out.extend_from_slice(&u32::MAX.to_le_bytes());
+        out.extend_from_slice(&u32::MAX.to_le_bytes());
+        out.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        out.extend_from_slice(&28u16.to_le_bytes());
+        out.extend_from_slice(&[0u8; 6]); // comment, disk, internal attributes
+        out.extend_from_slice(&0u32.to_le_bytes()); // external attributes
+        out.extend_from_slice(&u32::MAX.to_le_bytes());
+        out.extend_from_slice(name);
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&24u16.to_le_bytes());
+        out.extend_from_slice(&size.to_le_bytes());
+        out.extend_from_slice(&size.to_le_bytes());
+        out.extend_from_slice(&0u64.to_le_bytes());
+        let cd_size = out.len() as u64 - cd_offset;
+
+        // ZIP64 end of central directory record and locator
+        let eocd64_offset = out.len() as u64;
+        out.extend_from_slice(&crate::END_OF_CENTRAL_DIR_SIGNATURE64.to_le_bytes());
+        out.extend_from_slice(&44u64.to_le_bytes());
+        out.extend_from_slice(&45u16.to_le_bytes());
+        out.extend_from_slice(&45u16.to_le_bytes());
+        out.extend_from_slice(&[0u8; 8]); // disk numbers
+        out.extend_from_slice(&1u64.to_le_bytes());
+        out.extend_from_slice(&1u64.to_le_bytes());
+        out.extend_from_slice(&cd_size.to_le_bytes());
+        out.extend_from_slice(&cd_offset.to_le_bytes());
+        out.extend_from_slice(&END_OF_CENTRAL_DIR_LOCATOR_SIGNATURE.to_le_bytes());
+        out.extend_from_slice(&0u32.to_le_bytes());
+        out.extend_from_slice(&eocd64_offset.to_le_bytes());
+        out.extend_from_slice(&1u32.to_le_bytes());
+
+        // End of central directory record
+        let eocd = out.len();
+        out.extend_from_slice(&END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES);
+        out.extend_from_slice(&[0u8; 4]);
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&1u16.to_le_bytes());
+        out.extend_from_slice(&(cd_size as u32).to_le_bytes());
+        out.extend_from_slice(&(cd_offset as u32).to_le_bytes());
+        out.extend_from_slice(&0u16.to_le_bytes());
+
+        // num_entries, total_entries, central_dir_size, central_dir_offset
+        let fields = [(8, 2), (10, 2), (12, 4), (16, 4)];
+        for &field in saturated {
+            let (pos, len) = fields[field];
+            out[eocd + pos..eocd + pos + len].fill(0xFF);
+        }
+
+        out
+    }
+
+    #[rstest]
+    #[case(&[0])]
+    #[case(&[1])]
+    #[case(&[2])]
+    #[case(&[3])]
+    #[case(&[0, 1, 2, 3])]
+    #[test]
+    fn test_locate_zip64_end_of_central_directory(#[case] saturated: &[usize]) {
+        let payload = b"<worksheet><sheetData/></worksheet>";
+        let data = forged_zip64_archive(payload, saturated);
+
+        let archive = ZipLocator::new().locate_in_slice(&data).unwrap();
+        assert_eq!(archive.entries_hint(), 1);
+        let entries = archive.entries().collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].uncompressed_size_hint(), payload.len() as u64);
+        assert_eq!(entries[0].compressed_size_hint(), payload.len() as u64);
+        assert_eq!(entries[0].local_header_offset(), 0);
+        let entry = archive.get_entry(entries[0].wayfinder()).unwrap();
+        assert_eq!(entry.data(), payload);
+
+        let mut buffer = vec![0u8; crate::RECOMMENDED_BUFFER_SIZE];
+        let archive = ZipLocator::new()
+            .locate_in_reader(data.as_slice(), &mut buffer, data.len() as u64)
+            .unwrap();
+        assert_eq!(archive.entries_hint(), 1);
+        let mut entries = archive.entries(&mut buffer);
+        let entry = entries.next_entry().unwrap().unwrap();
+        assert_eq!(entry.uncompressed_size_hint(), payload.len() as u64);
+        assert_eq!(entry.local_header_offset(), 0);
+        assert!(entries.next_entry().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_locate_zip64_missing_locator() {
+        let mut data = forged_zip64_archive(b"data", &[3]);
+        let eocd = data.len() - EndOfCentralDirectoryRecordFixed::SIZE;
+        let locator = eocd - Zip64EndOfCentralDirectoryLocatorRecord::SIZE;
+        data[locator..locator + 4].fill(0);
+        assert!(ZipLocator::new().locate_in_slice(&data).is_err());
+
+        let mut buffer = vec![0u8; crate::RECOMMENDED_BUFFER_SIZE];
+        let end_offset = data.len() as u64;
+        let result = ZipLocator::new().locate_in_reader(data.as_slice(), &mut buffer, end_offset);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_locate_saturated_entries_without_zip64() {
+        let mut archive = crate::ZipArchiveWriter::new(Vec::new());
+        archive.write_stored_file("a.txt", b"a").unwrap();
+        archive.write_stored_file("b.txt", b"b").unwrap();
+        let mut data = archive.finish().unwrap();
+
+        let eocd = data.len() - EndOfCentralDirectoryRecordFixed::SIZE;
+        data[eocd + 8..eocd + 12].fill(0xFF);
+
+        let archive = ZipLocator::new().locate_in_slice(&data).unwrap();
+        assert_eq!(archive.entries_hint(), u64::from(u16::MAX));
+        assert_eq!(archive.entries().count(), 2);
+
+        let mut buffer = vec![0u8; crate::RECOMMENDED_BUFFER_SIZE];
+        let end_offset = data.len() as u64;
+        let archive = ZipLocator::new()
+            .locate_in_reader(data.as_slice(), &mut buffer, end_offset)
+            .unwrap();
+        let mut entries = archive.entries(&mut buffer);
+        assert!(entries.next_entry().unwrap().is_some());
+        assert!(entries.next_entry().unwrap().is_some());
+        assert!(entries.next_entry().unwrap().is_none());
+    }
 }
@@ -33,16 +33,32 @@
 //! let bytes = writer.finish()?;
 //! # Ok::<(), Box<dyn std::error::Error>>(())
 //! ```
+//!
+//! # Editing Archives
+//!
+//! ```rust,no_run
+//! use soapberry_zip::office::ArchiveEditor;
+//!
+//! let data = std::fs::read("document.docx")?;
+//! let mut editor = ArchiveEditor::from_slice(&data)?;
+//! // Untouched entries are copied without being recompressed
+//! editor.write_deflated("word/document.xml", b"<w:document/>");
+//! let bytes = editor.finish_to_bytes()?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
 
+use crate::recovery::scan_local_headers;
 use crate::{
-    CompressionMethod, Error, ErrorKind, ZipArchive, ZipArchiveWriter, ZipSliceArchive,
-    ZipVerification,
+    CompressionMethod, Error, ErrorKind, FileReader, RECOMMENDED_BUFFER_SIZE, RangeReader,
+    ReaderAt, ZipArchive, ZipArchiveWriter, ZipFileHeaderRecord, ZipLocator, ZipSliceArchive,
+    ZipSliceVerifier, ZipVerification,
 };
 use flate2::Compression;
 use flate2::read::DeflateDecoder;
 use flate2::write::DeflateEncoder;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::{Read, Write};
+use std::path::Path;
 
 /// High-performance ZIP archive reader for Office document formats.
 ///
@@ -56,7 +72,10 @@ use std::io::{Read, Write};
 /// - Lazy decompression - only decompress files when accessed
 /// - Pre-indexed file lookup for O(1) access by name
 pub struct ArchiveReader<'data> {
-    archive: ZipSliceArchive<&'data [u8]>,
+    data: &'data [u8],
+    /// The parsed central directory, or `None` if the entries were recovered
+    /// from local file headers
+    archive: Option<ZipSliceArchive<&'data [u8]>>,
     /// Pre-built index for fast file lookup by name
     index: HashMap<String, EntryInfo>,
 }
@@ -64,11 +83,23 @@ pub struct ArchiveReader<'data> {
 /// Information about an archive entry for fast lookup
 #[derive(Debug, Clone)]
 struct EntryInfo {
-    wayfinder: crate::ZipArchiveEntryWayfinder,
+    location: EntryLocation,
     compression_method: CompressionMethod,
     uncompressed_size: u64,
 }
 
+/// Where an entry's data is found
+#[derive(Debug, Clone)]
+enum EntryLocation {
+    /// Listed in the central directory
+    Central(crate::ZipArchiveEntryWayfinder),
+    /// Found by scanning local file headers
+    Local {
+        data: std::ops::Range<usize>,
+        verifier: ZipVerification,
+    },
+}
+
 impl<'data> ArchiveReader<'data> {
     /// Create a new archive reader from a byte slice.
     ///
@@ -100,14 +131,67 @@ impl<'data> ArchiveReader<'data> {
             index.insert(
                 name,
                 EntryInfo {
-                    wayfinder: entry.wayfinder(),
+                    location: EntryLocation::Central(entry.wayfinder()),
                     compression_method: entry.compression_method(),
                     uncompressed_size: entry.uncompressed_size_hint(),
                 },
             );
         }
 
-        Ok(Self { archive, index })
+        Ok(Self {
+            data,
+            archive: Some(archive),
+            index,
+        })
+    }
+
+    /// Create an archive reader that tolerates a damaged central directory.
+    ///
+    /// The central directory is used when it can be read. Otherwise the
+    /// entries are recovered by scanning the local file headers from the
+    /// start of the archive, which finds every entry in front of the damage
+    /// of a truncated file. Use [`is_recovered()`](Self::is_recovered) to
+    /// tell the two cases apart.
+    ///
+    /// Fails only if not a single entry could be recovered.
+    pub fn recover(data: &'data [u8]) -> Result<Self, Error> {
+        let error = match Self::new(data) {
+            Ok(reader) => return Ok(reader),
+            Err(error) => error,
+        };
+
+        let index: HashMap<String, EntryInfo> = scan_local_headers(data)
+            .into_iter()
+            .filter(|entry| !entry.is_dir)
+            .map(|entry| {
+                let info = EntryInfo {
+                    location: EntryLocation::Local {
+                        data: entry.data,
+                        verifier: entry.verifier,
+                    },
+                    compression_method: entry.compression_method,
+                    uncompressed_size: entry.verifier.uncompressed_size,
+                };
+                (entry.name, info)
+            })
+            .collect();
+
+        if index.is_empty() {
+            return Err(error);
+        }
+
+        Ok(Self {
+            data,
+            archive: None,
+            index,
+        })
+    }
+
+    /// Whether the entries were recovered from local file headers because the
+    /// central directory could not be read.
+    #[inline]
+    pub fn is_recovered(&self) -> bool {
+        self.archive.is_none()
     }
 
     /// Get the number of files in the archive (excluding directories).
@@ -139,65 +223,72 @@ impl<'data> ArchiveReader<'data> {
         self.index.keys().map(|s| s.as_str())
     }
 
+    /// Get the decompressed size of a file as recorded in the archive.
+    ///
+    /// The size comes from the archive headers, so it is only a hint for
+    /// damaged or malicious archives.
+    pub fn uncompressed_size(&self, name: &str) -> Option<u64> {
+        self.entry_info(name)
+            .ok()
+            .map(|info| info.uncompressed_size)
+    }
+
     /// Read and decompress a file from the archive.
     ///
     /// Returns the decompressed contents of the file. Supports both stored
     /// (uncompressed) and deflated entries.
     pub fn read(&self, name: &str) -> Result<Vec<u8>, Error> {
-        // Normalize name - remove leading slash if present
-        let normalized = name.strip_prefix('/').unwrap_or(name);
-
-        let info = self
-            .index
-            .get(normalized)
-            .ok_or_else(|| Error::from(ErrorKind::FileNotFound(normalized.to_string())))?;
-
-        let entry = self.archive.get_entry(info.wayfinder)?;
-        let data = entry.data();
+        let size = self.entry_info(name)?.uncompressed_size;
+        let mut data = Vec::with_capacity(size as usize);
+        self.read_stream(name)?.read_to_end(&mut data)?;
+        Ok(data)
+    }
 
-        match info.compression_method {
-            CompressionMethod::Store => {
-                // Stored (uncompressed) - verify and return directly
-                let verifier = entry.claim_verifier();
-                verifier.valid(ZipVerification {
-                    crc: crate::crc32(data),
-                    uncompressed_size: data.len() as u64,
-                })?;
-                Ok(data.to_vec())
+    /// Open a file for streaming decompression.
+    ///
+    /// Unlike [`read()`](Self::read), the decompressed contents are never held
+    /// in memory as a whole, which keeps memory flat for very large parts. The
+    /// CRC32 and size are verified incrementally; a mismatch surfaces as an
+    /// [`std::io::ErrorKind::InvalidData`] error from the final read.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use soapberry_zip::office::ArchiveReader;
+    /// use std::io::{BufRead, BufReader};
+    ///
+    /// let data = std::fs::read("workbook.xlsx")?;
+    /// let archive = ArchiveReader::new(&data)?;
+    ///
+    /// let reader = BufReader::new(archive.read_stream("xl/sharedStrings.xml")?);
+    /// for line in reader.lines() {
+    ///     println!("{}", line?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn read_stream(&self, name: &str) -> Result<impl Read + Send + '_, Error> {
+        let info = self.entry_info(name)?;
+        let (data, verifier) = match (&info.location, &self.archive) {
+            (EntryLocation::Central(wayfinder), Some(archive)) => {
+                let entry = archive.get_entry(*wayfinder)?;
+                (entry.data(), entry.claim_verifier())
             },
-            CompressionMethod::Deflate => {
-                // Deflate - decompress with pre-allocated buffer
-                // Using unsafe to avoid costly buffer zeroing from read_to_end
-                let size = info.uncompressed_size as usize;
-                let mut decompressed = Vec::with_capacity(size);
-
-                // SAFETY: We set the length to the expected uncompressed size.
-                // The decompression will write exactly `size` bytes (verified by CRC32).
-                // Any unwritten bytes at the end are truncated after reading.
-                #[allow(unsafe_code, clippy::uninit_vec)]
-                unsafe {
-                    decompressed.set_len(size);
-                }
+            (EntryLocation::Local { data, verifier }, _) => (&self.data[data.clone()], *verifier),
+            (EntryLocation::Central(_), None) => {
+                unreachable!("central directory entries come from a parsed archive")
+            },
+        };
 
-                let mut decoder = entry.verifying_reader(DeflateDecoder::new(data));
-                let mut total_read = 0;
-                while total_read < size {
-                    match decoder.read(&mut decompressed[total_read..]) {
-                        Ok(0) => break,
-                        Ok(n) => total_read += n,
-                        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
-                        Err(e) => return Err(e.into()),
-                    }
-                }
+        EntryDecoder::new(data, info.compression_method, verifier)
+    }
 
-                // Truncate to actual bytes read (handles size mismatch gracefully)
-                decompressed.truncate(total_read);
-                Ok(decompressed)
-            },
-            other => Err(Error::from(ErrorKind::UnsupportedCompressionMethod(
-                other.as_id().as_u16(),
-            ))),
-        }
+    /// Look up the index entry for a file name.
+    fn entry_info(&self, name: &str) -> Result<&EntryInfo, Error> {
+        // Normalize name - remove leading slash if present
+        let normalized = name.strip_prefix('/').unwrap_or(name);
+
+        self.index
+            .get(normalized)
+            .ok_or_else(|| Error::from(ErrorKind::FileNotFound(normalized.to_string())))
     }
 
     /// Read a file as a UTF-8 string.
@@ -270,17 +361,59 @@ impl<'data> ArchiveReader<'data> {
     }
 }
 
+/// Decompressing reader for a single entry, returned by
+/// [`ArchiveReader::read_stream`] and [`FileArchive::read_stream`].
+enum EntryDecoder<R> {
+    Stored(ZipSliceVerifier<R>),
+    Deflate(ZipSliceVerifier<DeflateDecoder<R>>),
+}
+
+impl<R: Read> EntryDecoder<R> {
+    /// Wrap the compressed data of an entry in the matching decoder.
+    fn new(
+        data: R,
+        compression_method: CompressionMethod,
+        verifier: ZipVerification,
+    ) -> Result<Self, Error> {
+        match compression_method {
+            CompressionMethod::Store => {
+                Ok(EntryDecoder::Stored(ZipSliceVerifier::new(data, verifier)))
+            },
+            CompressionMethod::Deflate => Ok(EntryDecoder::Deflate(ZipSliceVerifier::new(
+                DeflateDecoder::new(data),
+                verifier,
+            ))),
+            other => Err(Error::from(ErrorKind::UnsupportedCompressionMethod(
+                other.as_id().as_u16(),
+            ))),
+        }
+    }
+}
+
+impl<R: Read> Read for EntryDecoder<R> {
+    #[inline]
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self {
+            EntryDecoder::Stored(reader) => reader.read(buf),
+            EntryDecoder::Deflate(reader) => reader.read(buf),
+        }
+    }
+}
+
 impl std::fmt::Debug for ArchiveReader<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ArchiveReader")
             .field("file_count", &self.index.len())
+            .field("recovered", &self.is_recovered())
             .finish()
     }
 }
 
 /// High-performance streaming ZIP archive writer for Office document formats.
 ///
-/// This is the recommended writer for creating complete ZIP archives.
+/// This is the recommended writer for creating complete ZIP archives. ZIP64
+/// records are written automatically once the entry count, an entry size or
+/// an offset no longer fits the classic format.
 pub struct StreamingArchiveWriter<W: Write> {
     archive: ZipArchiveWriter<W>,
 }
@@ -330,6 +463,18 @@ impl<W: Write> StreamingArchiveWriter<W> {
         Ok(())
     }
 
+    fn write_with(
+        &mut self,
+        name: &str,
+        data: &[u8],
+        compression_method: CompressionMethod,
+    ) -> Result<(), Error> {
+        match compression_method {
+            CompressionMethod::Store => self.write_stored(name, data),
+            _ => self.write_deflated(name, data),
+        }
+    }
+
     /// Finish writing the archive.
     pub fn finish(self) -> Result<W, Error> {
         self.archive.finish()
@@ -342,6 +487,232 @@ impl Default for StreamingArchiveWriter<std::io::Cursor<Vec<u8>>> {
     }
 }
 
+/// Edits an existing ZIP archive without rewriting its untouched entries.
+///
+/// Entries that are neither replaced nor removed are copied byte-for-byte
+/// from the source archive, so they are never decompressed or recompressed.
+/// Replaced entries keep their original position, which matters for formats
+/// such as ODF that require `mimetype` to come first; new entries are
+/// appended in the order they were added. The central directory is rebuilt
+/// from scratch.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use soapberry_zip::office::ArchiveEditor;
+///
+/// let data = std::fs::read("document.docx")?;
+/// let mut editor = ArchiveEditor::from_slice(&data)?;
+/// editor.write_deflated("word/document.xml", b"<w:document/>");
+/// editor.remove("docProps/thumbnail.jpeg");
+/// let bytes = editor.finish_to_bytes()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct ArchiveEditor<R> {
+    source: ZipArchive<R>,
+    /// Entries in the source archive by normalized name
+    names: HashMap<String, EntryStamp>,
+    /// Entries the source archive had when it was opened, if it is read
+    /// from a file that may have changed since
+    expected: Option<HashMap<String, EntryStamp>>,
+    /// Pending replacements, additions and removals by entry name
+    edits: HashMap<String, Edit>,
+    /// Names of entries that are not in the source archive, in insertion order
+    added: Vec<String>,
+}
+
+/// CRC32 and size of the uncompressed data of an entry, as declared in the
+/// central directory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct EntryStamp {
+    crc32: u32,
+    uncompressed_size: u64,
+}
+
+impl EntryStamp {
+    fn of(record: &ZipFileHeaderRecord<'_>) -> Self {
+        Self {
+            crc32: record.crc32(),
+            uncompressed_size: record.uncompressed_size_hint(),
+        }
+    }
+}
+
+/// A pending change to a single archive entry
+enum Edit {
+    Write {
+        data: Vec<u8>,
+        compression_method: CompressionMethod,
+    },
+    Remove,
+}
+
+impl<'data> ArchiveEditor<std::io::Cursor<&'data [u8]>> {
+    /// Create an editor over an archive held in memory.
+    pub fn from_slice(data: &'data [u8]) -> Result<Self, Error> {
+        Self::new(ZipArchive::from_slice(data)?.into_zip_archive())
+    }
+}
+
+impl<R: ReaderAt> ArchiveEditor<R> {
+    /// Create an editor over an existing archive.
+    ///
+    /// Only the central directory is read up front; entry data is read while
+    /// the edited archive is written.
+    pub fn new(source: ZipArchive<R>) -> Result<Self, Error> {
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let mut names = HashMap::new();
+        let mut entries = source.entries(&mut buffer);
+        while let Some(entry) = entries.next_entry()? {
+            names.insert(entry_name(&entry), EntryStamp::of(&entry));
+        }
+
+        Ok(Self {
+            source,
+            names,
+            expected: None,
+            edits: HashMap::new(),
+            added: Vec::new(),
+        })
+    }
+
+    /// Get the number of entries the edited archive will contain.
+    pub fn len(&self) -> usize {
+        self.names
+            .keys()
+            .chain(&self.added)
+            .filter(|name| self.contains(name))
+            .count()
+    }
+
+    /// Check if the edited archive will be empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Check if the edited archive will contain an entry with the given name.
+    pub fn contains(&self, name: &str) -> bool {
+        match self.edits.get(name) {
+            Some(Edit::Write { .. }) => true,
+            Some(Edit::Remove) => false,
+            None => self.names.contains_key(name),
+        }
+    }
+
+    /// Get the names of the entries in the source archive (in arbitrary order).
+    pub fn source_names(&self) -> impl Iterator<Item = &str> {
+        self.names.keys().map(|s| s.as_str())
+    }
+
+    /// Check if the source archive has an entry with the given name that can
+    /// be copied as is.
+    ///
+    /// For editors over a [`FileArchive`], the entry must also have the CRC32
+    /// and size it had when the archive was opened; entries that changed
+    /// since should be written again.
+    pub fn is_source_unchanged(&self, name: &str) -> bool {
+        self.names.get(name).is_some_and(|stamp| {
+            self.expected
+                .as_ref()
+                .map_or(true, |expected| expected.get(name) == Some(stamp))
+        })
+    }
+
+    /// Replace or add an entry without compression (stored).
+    pub fn write_stored(&mut self, name: &str, data: impl Into<Vec<u8>>) {
+        self.write(name, data.into(), CompressionMethod::Store);
+    }
+
+    /// Replace or add an entry with Deflate compression.
+    pub fn write_deflated(&mut self, name: &str, data: impl Into<Vec<u8>>) {
+        self.write(name, data.into(), CompressionMethod::Deflate);
+    }
+
+    /// Remove an entry, returning whether it was present.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let present = self.contains(name);
+        self.added.retain(|n| n != name);
+        if self.names.contains_key(name) {
+            self.edits.insert(name.to_string(), Edit::Remove);
+        } else {
+            self.edits.remove(name);
+        }
+        present
+    }
+
+    fn write(&mut self, name: &str, data: Vec<u8>, compression_method: CompressionMethod) {
+        let edit = Edit::Write {
+            data,
+            compression_method,
+        };
+        if self.edits.insert(name.to_string(), edit).is_none() && !self.names.contains_key(name) {
+            self.added.push(name.to_string());
+        }
+    }
+
+    /// Write the edited archive to `writer`.
+    ///
+    /// Fails if an entry that is copied from a [`FileArchive`] changed since
+    /// the archive was opened; see
+    /// [`is_source_unchanged`](Self::is_source_unchanged).
+    pub fn finish<W: Write>(self, writer: W) -> Result<W, Error> {
+        let mut output = StreamingArchiveWriter::with_writer(writer);
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let mut entries = self.source.entries(&mut buffer);
+        while let Some(record) = entries.next_entry()? {
+            let name = entry_name(&record);
+            match self.edits.get(&name) {
+                None if !self.is_source_unchanged(&name) => {
+                    return Err(Error::from(ErrorKind::FileChanged(name)));
+                },
+                None => output.archive.copy_entry(&self.source, &record)?,
+                Some(Edit::Remove) => {},
+                Some(Edit::Write {
+                    data,
+                    compression_method,
+                }) => output.write_with(&name, data, *compression_method)?,
+            }
+        }
+
+        for name in &self.added {
+            if let Some(Edit::Write {
+                data,
+                compression_method,
+            }) = self.edits.get(name)
+            {
+                output.write_with(name, data, *compression_method)?;
+            }
+        }
+
+        output.finish()
+    }
+
+    /// Write the edited archive to memory and return its bytes.
+    pub fn finish_to_bytes(self) -> Result<Vec<u8>, Error> {
+        let cursor = self.finish(std::io::Cursor::new(Vec::new()))?;
+        Ok(cursor.into_inner())
+    }
+}
+
+impl<R> std::fmt::Debug for ArchiveEditor<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArchiveEditor")
+            .field("source_count", &self.names.len())
+            .field("edit_count", &self.edits.len())
+            .field("added_count", &self.added.len())
+            .finish()
+    }
+}
+
+/// Normalize an entry path the same way the readers index it.
+fn entry_name(entry: &ZipFileHeaderRecord<'_>) -> String {
+    let path = entry.file_path();
+    match path.try_normalize() {
+        Ok(normalized) => normalized.as_ref().to_string(),
+        Err(_) => String::from_utf8_lossy(path.as_ref()).to_string(),
+    }
+}
+
 // Ensure ArchiveReader is Send + Sync for parallel iteration
 // This is a compile-time assertion
 const _: () = {
@@ -383,11 +754,29 @@ pub struct LazyArchiveReader<'data> {
 impl<'data> LazyArchiveReader<'data> {
     /// Create a new lazy archive reader from a byte slice.
     pub fn new(data: &'data [u8]) -> Result<Self, Error> {
-        let inner = ArchiveReader::new(data)?;
-        Ok(Self {
+        Ok(Self::with_reader(ArchiveReader::new(data)?))
+    }
+
+    /// Create a lazy archive reader that tolerates a damaged central directory.
+    ///
+    /// See [`ArchiveReader::recover`].
+    pub fn recover(data: &'data [u8]) -> Result<Self, Error> {
+        Ok(Self::with_reader(ArchiveReader::recover(data)?))
+    }
+
+    fn with_reader(inner: ArchiveReader<'data>) -> Self {
+        Self {
             inner,
             cache: std::sync::RwLock::new(HashMap::new()),
-        })
+        }
+    }
+
+    /// Whether the entries were recovered from local file headers.
+    ///
+    /// See [`ArchiveReader::is_recovered`].
+    #[inline]
+    pub fn is_recovered(&self) -> bool {
+        self.inner.is_recovered()
     }
 
     /// Get the number of files in the archive.
@@ -413,6 +802,13 @@ impl<'data> LazyArchiveReader<'data> {
         self.inner.file_names()
     }
 
+    /// Get the decompressed size of a file without decompressing it.
+    ///
+    /// See [`ArchiveReader::uncompressed_size`].
+    pub fn uncompressed_size(&self, name: &str) -> Option<u64> {
+        self.inner.uncompressed_size(name)
+    }
+
     /// Read and decompress a file, using cache if available.
     ///
     /// Returns a cloned Vec for API compatibility. For zero-copy access,
@@ -452,6 +848,14 @@ impl<'data> LazyArchiveReader<'data> {
         Ok(arc)
     }
 
+    /// Open a file for streaming decompression, bypassing the cache.
+    ///
+    /// See [`ArchiveReader::read_stream`]. Nothing is cached, so this is the
+    /// right choice for large parts that are consumed once.
+    pub fn read_stream(&self, name: &str) -> Result<impl Read + Send + '_, Error> {
+        self.inner.read_stream(name)
+    }
+
     /// Read multiple files in parallel WITHOUT caching.
     ///
     /// This is the fastest method for bulk decompression when you need to read
@@ -594,10 +998,301 @@ const _: () = {
     assert_send_sync::<LazyArchiveReader<'static>>();
 };
 
+/// ZIP archive read from a file with positioned reads.
+///
+/// Unlike [`ArchiveReader`], the archive is never loaded into memory: only the
+/// central directory is read, and entry data is read from the file as it is
+/// decompressed. The names, sizes and CRC32s of the entries are recorded when
+/// the archive is opened, and the central directory is located again for
+/// every read. The archive therefore stays usable after its file is
+/// overwritten with a new archive, but only entries that still have the
+/// recorded CRC32 and size can be read or copied from it; the others fail with
+/// [`ErrorKind::FileChanged`].
+///
+/// # Example
+/// ```rust,no_run
+/// use soapberry_zip::office::FileArchive;
+/// use std::io::Read;
+///
+/// let archive = FileArchive::open("workbook.xlsx")?;
+/// let mut xml = String::new();
+/// archive.read_stream("xl/workbook.xml")?.read_to_string(&mut xml)?;
+///
+/// // Untouched entries are copied from the file without being recompressed
+/// let mut editor = archive.editor()?;
+/// editor.write_deflated("xl/workbook.xml", xml.into_bytes());
+/// let bytes = editor.finish_to_bytes()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug)]
+pub struct FileArchive {
+    file: FileReader,
+    /// The entries when the archive was opened, by name
+    index: HashMap<String, EntryStamp>,
+    /// Entry names in central directory order
+    names: Vec<String>,
+}
+
+impl FileArchive {
+    /// Open the archive at `path`.
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        Self::from_file(std::fs::File::open(path)?)
+    }
+
+    /// Read an archive from an open file, failing if it is not a ZIP archive.
+    pub fn from_file(file: std::fs::File) -> Result<Self, Error> {
+        let mut archive = Self {
+            file: FileReader::from(file),
+            index: HashMap::new(),
+            names: Vec::new(),
+        };
+
+        let mut index = HashMap::new();
+        let mut names = Vec::new();
+        {
+            let source = archive.archive()?;
+            let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+            let mut entries = source.entries(&mut buffer);
+            while let Some(entry) = entries.next_entry()? {
+                if entry.is_dir() {
+                    continue;
+                }
+                let name = entry_name(&entry);
+                if index.insert(name.clone(), EntryStamp::of(&entry)).is_none() {
+                    names.push(name);
+                }
+            }
+        }
+        archive.index = index;
+        archive.names = names;
+        Ok(archive)
+    }
+
+    /// Get the number of files the archive had when it was opened.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Check if the archive had no files when it was opened.
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Check if the archive had a file with the given name when it was opened.
+    pub fn contains(&self, name: &str) -> bool {
+        self.index
+            .contains_key(name.strip_prefix('/').unwrap_or(name))
+    }
+
+    /// Get the names of the files the archive had when it was opened.
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Get the decompressed size of a file as recorded when the archive was
+    /// opened.
+    ///
+    /// See [`ArchiveReader::uncompressed_size`].
+    pub fn uncompressed_size(&self, name: &str) -> Option<u64> {
+        self.index
+            .get(name.strip_prefix('/').unwrap_or(name))
+            .map(|stamp| stamp.uncompressed_size)
+    }
+
+    /// Locate the central directory in the file as it is now.
+    fn archive(&self) -> Result<ZipArchive<&FileReader>, Error> {
+        let end_offset = self.file.file_len()?;
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        ZipLocator::new()
+            .locate_in_reader(&self.file, &mut buffer, end_offset)
+            .map_err(|(_, e)| e)
+    }
+
+    /// Read and decompress a file from the archive.
+    pub fn read(&self, name: &str) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::new();
+        self.read_stream(name)?.read_to_end(&mut data)?;
+        Ok(data)
+    }
+
+    /// Open a file for streaming decompression.
+    ///
+    /// See [`ArchiveReader::read_stream`]. The compressed data is read from
+    /// the file as the stream is consumed.
+    pub fn read_stream(&self, name: &str) -> Result<impl Read + Send + '_, Error> {
+        // Normalize name - remove leading slash if present
+        let normalized = name.strip_prefix('/').unwrap_or(name);
+
+        let archive = self.archive()?;
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let mut entries = archive.entries(&mut buffer);
+        let (wayfinder, compression_method) = loop {
+            match entries.next_entry()? {
+                Some(entry) if !entry.is_dir() && entry_name(&entry) == normalized => {
+                    if !self.is_unchanged(normalized, &entry) {
+                        return Err(Error::from(ErrorKind::FileChanged(normalized.to_string())));
+                    }
+                    break (entry.wayfinder(), entry.compression_method());
+                },
+                Some(_) => {},
+                None => return Err(Error::from(ErrorKind::FileNotFound(normalized.to_string()))),
+            }
+        };
+
+        self.entry_decoder(&archive, wayfinder, compression_method)
+    }
+
+    /// Read and decompress multiple files in parallel.
+    ///
+    /// The central directory is located once for all of them. Returns a
+    /// HashMap mapping file names to their decompressed contents; files that
+    /// are missing, changed or fail to decompress are not included in the
+    /// result.
+    pub fn read_many_parallel(&self, names: &[&str]) -> HashMap<String, Vec<u8>> {
+        use rayon::prelude::*;
+
+        let Ok(archive) = self.archive() else {
+            return HashMap::new();
+        };
+        let wanted: HashSet<&str> = names
+            .iter()
+            .map(|name| name.strip_prefix('/').unwrap_or(name))
+            .collect();
+        let mut found = Vec::with_capacity(wanted.len());
+        let mut buffer = vec![0u8; RECOMMENDED_BUFFER_SIZE];
+        let mut entries = archive.entries(&mut buffer);
+        while let Ok(Some(entry)) = entries.next_entry() {
+            let name = entry_name(&entry);
+            if !entry.is_dir() && wanted.contains(name.as_str()) && self.is_unchanged(&name, &entry)
+            {
+                found.push((name, entry.wayfinder(), entry.compression_method()));
+            }
+        }
+
+        found
+            .into_par_iter()
+            .filter_map(|(name, wayfinder, compression_method)| {
+                let mut data = Vec::new();
+                self.entry_decoder(&archive, wayfinder, compression_method)
+                    .and_then(|mut decoder| Ok(decoder.read_to_end(&mut data)?))
+                    .ok()
+                    .map(|_| (name, data))
+            })
+            .collect()
+    }
+
+    /// Check that an entry still has the CRC32 and size recorded at open.
+    fn is_unchanged(&self, name: &str, entry: &ZipFileHeaderRecord<'_>) -> bool {
+        self.index.get(name) == Some(&EntryStamp::of(entry))
+    }
+
+    /// Open a decompressing reader over the data of an entry of `archive`.
+    fn entry_decoder(
+        &self,
+        archive: &ZipArchive<&FileReader>,
+        wayfinder: crate::ZipArchiveEntryWayfinder,
+        compression_method: CompressionMethod,
+    ) -> Result<impl Read + Send + '_, Error> {
+        let entry = archive.get_entry(wayfinder)?;
+        let (start, end) = entry.compressed_data_range();
+        let verifier = entry.reader().claim_verifier()?;
+        EntryDecoder::new(
+            RangeReader::new(&self.file, start..end),
+            compression_method,
+            verifier,
+        )
+    }
+
+    /// Start editing the archive.
+    ///
+    /// Entries that are not replaced or removed are copied from the file when
+    /// the editor is finished. They must not have changed since the archive
+    /// was opened; see [`ArchiveEditor::is_source_unchanged`].
+    pub fn editor(&self) -> Result<ArchiveEditor<&FileReader>, Error> {
+        let mut editor = ArchiveEditor::new(self.archive()?)?;
+        editor.expected = Some(self.index.clone());
+        Ok(editor)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// An archive of three deflated entries, and the offset of its central directory.
+    fn archive_with_three_entries() -> (Vec<u8>, usize) {
+        let mut writer = StreamingArchiveWriter::new();
+        writer.write_deflated("a.xml", b"<a>first</a>").unwrap();
+        writer.write_stored("b.txt", b"second").unwrap();
+        writer
+            .write_deflated("c/d.xml", &b"<d/>".repeat(1000))
+            .unwrap();
+        let bytes = writer.finish_to_bytes().unwrap();
+        let directory = ZipArchive::from_slice(&bytes).unwrap().directory_offset() as usize;
+        (bytes, directory)
+    }
+
+    #[test]
+    fn test_recover_intact_archive() {
+        let (bytes, _) = archive_with_three_entries();
+        let reader = ArchiveReader::recover(&bytes).unwrap();
+        assert!(!reader.is_recovered());
+        assert_eq!(reader.len(), 3);
+        assert_eq!(reader.uncompressed_size("/c/d.xml"), Some(4000));
+        assert_eq!(reader.uncompressed_size("missing.xml"), None);
+    }
+
+    #[test]
+    fn test_recover_truncated_central_directory() {
+        let (bytes, directory) = archive_with_three_entries();
+        let truncated = &bytes[..directory + 20];
+        assert!(ArchiveReader::new(truncated).is_err());
+
+        let reader = ArchiveReader::recover(truncated).unwrap();
+        assert!(reader.is_recovered());
+        assert_eq!(reader.len(), 3);
+        assert_eq!(reader.read("a.xml").unwrap(), b"<a>first</a>");
+        assert_eq!(reader.read("b.txt").unwrap(), b"second");
+        assert_eq!(reader.read("c/d.xml").unwrap(), b"<d/>".repeat(1000));
+    }
+
+    #[test]
+    fn test_recover_drops_cut_off_entry() {
+        let (bytes, directory) = archive_with_three_entries();
+        let reader = ArchiveReader::recover(&bytes[..directory - 30]).unwrap();
+        assert!(reader.is_recovered());
+        assert!(reader.contains("a.xml"));
+        assert!(reader.contains("b.txt"));
+        assert!(!reader.contains("c/d.xml"));
+    }
+
+    #[test]
+    fn test_recover_stored_entry_with_data_descriptor() {
+        let mut archive = ZipArchiveWriter::new(std::io::Cursor::new(Vec::new()));
+        let (mut entry, config) = archive
+            .new_file("stored.txt")
+            .compression_method(CompressionMethod::Store)
+            .start()
+            .unwrap();
+        let mut writer = config.wrap(&mut entry);
+        writer.write_all(b"stored with a descriptor").unwrap();
+        let (_, desc) = writer.finish().unwrap();
+        entry.finish(desc).unwrap();
+        let bytes = archive.finish().unwrap().into_inner();
+        let directory = ZipArchive::from_slice(&bytes).unwrap().directory_offset() as usize;
+
+        let reader = LazyArchiveReader::recover(&bytes[..directory]).unwrap();
+        assert!(reader.is_recovered());
+        assert_eq!(
+            reader.read("stored.txt").unwrap(),
+            b"stored with a descriptor"
+        );
+    }
+
+    #[test]
+    fn test_recover_rejects_garbage() {
+        assert!(ArchiveReader::recover(b"definitely not a zip archive").is_err());
+    }
 
     #[test]
     fn test_round_trip_stored() {
@@ -639,4 +1334,255 @@ mod tests {
         assert_eq!(reader.read("content.xml").unwrap(), b"<content/>");
         assert_eq!(reader.read("styles.xml").unwrap(), b"<styles/>");
     }
+
+    #[test]
+    fn test_read_stream() {
+        let content: String = (0..2000)
+            .map(|i| format!("<si><t>string {i}</t></si>"))
+            .collect();
+        let mut writer = StreamingArchiveWriter::new();
+        writer
+            .write_deflated("xl/sharedStrings.xml", content.as_bytes())
+            .unwrap();
+        writer
+            .write_stored("mimetype", b"application/test")
+            .unwrap();
+        let bytes = writer.finish_to_bytes().unwrap();
+
+        let reader = ArchiveReader::new(&bytes).unwrap();
+        let mut stream = reader.read_stream("/xl/sharedStrings.xml").unwrap();
+        let mut streamed = Vec::new();
+        let mut chunk = [0u8; 7];
+        loop {
+            match stream.read(&mut chunk).unwrap() {
+                0 => break,
+                n => streamed.extend_from_slice(&chunk[..n]),
+            }
+        }
+        assert_eq!(streamed, content.as_bytes());
+
+        let mut stored = String::new();
+        reader
+            .read_stream("mimetype")
+            .unwrap()
+            .read_to_string(&mut stored)
+            .unwrap();
+        assert_eq!(stored, "application/test");
+
+        assert!(reader.read_stream("missing.xml").is_err());
+    }
+
+    #[test]
+    fn test_read_stream_detects_corruption() {
+        let mut writer = StreamingArchiveWriter::new();
+        writer.write_stored("data.txt", b"Hello, World!").unwrap();
+        let mut bytes = writer.finish_to_bytes().unwrap();
+        let pos = find_bytes(&bytes, b"Hello").unwrap();
+        bytes[pos] = b'J';
+
+        let reader = ArchiveReader::new(&bytes).unwrap();
+        let mut data = Vec::new();
+        let err = reader
+            .read_stream("data.txt")
+            .unwrap()
+            .read_to_end(&mut data)
+            .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(reader.read("data.txt").is_err());
+    }
+
+    fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack.windows(needle.len()).position(|w| w == needle)
+    }
+
+    #[rstest::rstest]
+    #[case(u16::MAX as usize - 1, false)]
+    #[case(u16::MAX as usize, true)]
+    #[case(u16::MAX as usize + 1, true)]
+    #[test]
+    fn test_zip64_entry_count_round_trip(#[case] count: usize, #[case] zip64: bool) {
+        let mut writer = StreamingArchiveWriter::new();
+        for i in 0..count - 1 {
+            writer
+                .write_stored(&format!("xl/media/{i}.bin"), &(i as u32).to_le_bytes())
+                .unwrap();
+        }
+        writer
+            .write_deflated("xl/workbook.xml", b"<workbook/>")
+            .unwrap();
+        let bytes = writer.finish_to_bytes().unwrap();
+
+        // EOCD (22 bytes) is preceded by the ZIP64 locator (20) and record (56)
+        let eocd64 = bytes.len() - 22 - 20 - 56;
+        let signature = crate::END_OF_CENTRAL_DIR_SIGNATURE64.to_le_bytes();
+        assert_eq!(bytes[eocd64..eocd64 + 4] == signature, zip64);
+
+        let reader = ArchiveReader::new(&bytes).unwrap();
+        assert_eq!(reader.len(), count);
+        assert_eq!(reader.read("xl/media/0.bin").unwrap(), 0u32.to_le_bytes());
+        let last = format!("xl/media/{}.bin", count - 2);
+        assert_eq!(
+            reader.read(&last).unwrap(),
+            (count as u32 - 2).to_le_bytes()
+        );
+        assert_eq!(reader.read("xl/workbook.xml").unwrap(), b"<workbook/>");
+    }
+
+    #[test]
+    fn test_archive_editor() {
+        let image: Vec<u8> = (0..64 * 1024).map(|i| (i * 7 % 251) as u8).collect();
+        let mut writer = StreamingArchiveWriter::new();
+        writer
+            .write_stored("mimetype", b"application/vnd.oasis.opendocument.text")
+            .unwrap();
+        writer
+            .write_deflated("content.xml", b"<office:document-content/>")
+            .unwrap();
+        writer.write_deflated("Pictures/1.png", &image).unwrap();
+        writer.write_deflated("Thumbnails/t.png", &image).unwrap();
+        let source = writer.finish_to_bytes().unwrap();
+
+        let mut editor = ArchiveEditor::from_slice(&source).unwrap();
+        editor.write_deflated(
+            "content.xml",
+            b"<office:document-content>2</office:document-content>",
+        );
+        editor.write_stored("META-INF/manifest.xml", b"<manifest:manifest/>");
+        assert!(editor.remove("Thumbnails/t.png"));
+        assert!(!editor.remove("missing.xml"));
+        assert!(!editor.contains("Thumbnails/t.png"));
+        assert_eq!(editor.len(), 4);
+        let edited = editor.finish_to_bytes().unwrap();
+
+        let reader = ArchiveReader::new(&edited).unwrap();
+        assert_eq!(reader.len(), 4);
+        assert!(!reader.contains("Thumbnails/t.png"));
+        assert_eq!(
+            reader.read("content.xml").unwrap(),
+            b"<office:document-content>2</office:document-content>"
+        );
+        assert_eq!(
+            reader.read("META-INF/manifest.xml").unwrap(),
+            b"<manifest:manifest/>"
+        );
+        assert_eq!(reader.read("Pictures/1.png").unwrap(), image);
+
+        // Replaced entries keep their slot and new ones are appended
+        let archive = ZipArchive::from_slice(&edited).unwrap();
+        let names: Vec<_> = archive.entries().map(|e| entry_name(&e.unwrap())).collect();
+        assert_eq!(
+            names,
+            [
+                "mimetype",
+                "content.xml",
+                "Pictures/1.png",
+                "META-INF/manifest.xml"
+            ]
+        );
+
+        // The untouched picture is copied without recompression
+        let compressed = |data: &[u8]| {
+            let archive = ZipArchive::from_slice(data).unwrap();
+            let entry = archive
+                .entries()
+                .map(|e| e.unwrap())
+                .find(|e| e.file_path().as_ref() == b"Pictures/1.png")
+                .unwrap();
+            archive
+                .get_entry(entry.wayfinder())
+                .unwrap()
+                .data()
+                .to_vec()
+        };
+        assert_eq!(compressed(&edited), compressed(&source));
+    }
+
+    #[test]
+    fn test_archive_editor_remove_then_add() {
+        let mut writer = StreamingArchiveWriter::new();
+        writer.write_deflated("a.xml", b"<a/>").unwrap();
+        writer.write_deflated("b.xml", b"<b/>").unwrap();
+        let source = writer.finish_to_bytes().unwrap();
+
+        let mut editor = ArchiveEditor::from_slice(&source).unwrap();
+        editor.write_deflated("c.xml", b"<c/>");
+        assert!(editor.remove("c.xml"));
+        assert!(editor.remove("a.xml"));
+        editor.write_deflated("a.xml", b"<a>2</a>");
+        let edited = editor.finish_to_bytes().unwrap();
+
+        let reader = ArchiveReader::new(&edited).unwrap();
+        assert_eq!(reader.len(), 2);
+        assert!(!reader.contains("c.xml"));
+        assert_eq!(reader.read("a.xml").unwrap(), b"<a>2</a>");
+        assert_eq!(reader.read("b.xml").unwrap(), b"<b/>");
+    }
+
+    #[test]
+    fn test_file_archive() {
+        let (bytes, _) = archive_with_three_entries();
+        let path =
+            std::env::temp_dir().join(format!("soapberry-file-archive-{}.zip", std::process::id()));
+        std::fs::write(&path, &bytes).unwrap();
+
+        let archive = FileArchive::open(&path).unwrap();
+        assert_eq!(archive.len(), 3);
+        assert!(archive.contains("/b.txt"));
+        assert_eq!(
+            archive.file_names().collect::<Vec<_>>(),
+            ["a.xml", "b.txt", "c/d.xml"]
+        );
+        assert_eq!(archive.uncompressed_size("c/d.xml"), Some(4000));
+        let many = archive.read_many_parallel(&["/a.xml", "c/d.xml", "missing.xml"]);
+        assert_eq!(many.len(), 2);
+        assert_eq!(many["a.xml"], b"<a>first</a>");
+        assert_eq!(many["c/d.xml"], b"<d/>".repeat(1000));
+        assert_eq!(archive.read("/a.xml").unwrap(), b"<a>first</a>");
+        assert_eq!(archive.read("b.txt").unwrap(), b"second");
+        let mut streamed = Vec::new();
+        archive
+            .read_stream("c/d.xml")
+            .unwrap()
+            .read_to_end(&mut streamed)
+            .unwrap();
+        assert_eq!(streamed, b"<d/>".repeat(1000));
+        assert!(archive.read("missing.xml").is_err());
+
+        let mut editor = archive.editor().unwrap();
+        editor.write_deflated("a.xml", b"<a>edited</a>");
+        editor.remove("b.txt");
+        let edited = editor.finish_to_bytes().unwrap();
+
+        // After the file is overwritten, only entries that are unchanged
+        // since the archive was opened can be read or copied
+        std::fs::write(&path, &edited).unwrap();
+        assert!(matches!(
+            archive.read("a.xml").unwrap_err().kind(),
+            ErrorKind::FileChanged(name) if name == "a.xml"
+        ));
+        assert!(archive.read("b.txt").is_err());
+        assert_eq!(archive.read("c/d.xml").unwrap(), b"<d/>".repeat(1000));
+        assert_eq!(
+            archive
+                .read_many_parallel(&["a.xml", "c/d.xml"])
+                .into_keys()
+                .collect::<Vec<_>>(),
+            ["c/d.xml"]
+        );
+
+        let editor = archive.editor().unwrap();
+        assert!(!editor.is_source_unchanged("a.xml"));
+        assert!(editor.is_source_unchanged("c/d.xml"));
+        assert!(editor.finish_to_bytes().is_err());
+        let mut editor = archive.editor().unwrap();
+        editor.write_deflated("a.xml", b"<a>again</a>");
+        let reader_bytes = editor.finish_to_bytes().unwrap();
+        let reader = ArchiveReader::new(&reader_bytes).unwrap();
+        assert_eq!(reader.read("a.xml").unwrap(), b"<a>again</a>");
+        assert_eq!(reader.read("c/d.xml").unwrap(), b"<d/>".repeat(1000));
+
+        std::fs::write(&path, b"not a zip").unwrap();
+        assert!(FileArchive::open(&path).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
 }
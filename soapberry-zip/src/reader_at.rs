@@ -106,6 +106,14 @@ impl FileReader {
         #[cfg(any(unix, windows))]
         return self.0;
     }
+
+    /// Returns the current length of the file.
+    pub(crate) fn file_len(&self) -> std::io::Result<u64> {
+        #[cfg(not(any(unix, windows)))]
+        return self.0.0.lock().unwrap().metadata().map(|m| m.len());
+        #[cfg(any(unix, windows))]
+        return self.0.metadata().map(|m| m.len());
+    }
 }
 
 impl ReaderAt for FileReader {
@@ -0,0 +1,205 @@
+//! Recovery of entries from archives whose central directory is unreadable.
+//!
+//! Damaged Office files are often truncated, which cuts off the central
+//! directory and the end of central directory record at the end of the
+//! archive. The local file headers in front of each entry's data are usually
+//! intact, so the entries that precede the damage can still be found by
+//! walking those headers from the start of the file.
+
+use crate::archive::{DataDescriptor, ZipLocalFileHeaderFixed};
+use crate::crc::crc32_chunk;
+use crate::path::ZipFilePath;
+use crate::utils::le_u32;
+use crate::{CompressionMethod, ZipVerification};
+use flate2::{Decompress, FlushDecompress, Status};
+use std::ops::Range;
+
+/// General purpose flag bit marking an entry followed by a data descriptor.
+const DATA_DESCRIPTOR_FLAG: u16 = 1 << 3;
+
+/// Signatures that may follow an entry's data descriptor.
+const NEXT_SIGNATURES: [u32; 4] = [
+    ZipLocalFileHeaderFixed::SIGNATURE,
+    crate::archive::CENTRAL_HEADER_SIGNATURE,
+    crate::archive::END_OF_CENTRAL_DIR_SIGNATURE64,
+    crate::locator::END_OF_CENTRAL_DIR_SIGNAUTRE,
+];
+
+/// An entry found by scanning local file headers.
+#[derive(Debug, Clone)]
+pub(crate) struct LocalEntry {
+    /// Entry name as it appears in the local header
+    pub(crate) name: String,
+    /// Whether the entry is a directory
+    pub(crate) is_dir: bool,
+    /// How the entry data is compressed
+    pub(crate) compression_method: CompressionMethod,
+    /// Byte range of the compressed data within the archive
+    pub(crate) data: Range<usize>,
+    /// Expected CRC and size of the decompressed data
+    pub(crate) verifier: ZipVerification,
+}
+
+/// Collect the entries whose local header and data are complete.
+///
+/// The scan stops at the central directory, at the first entry that is cut
+/// off, or at anything that is not a local file header.
+pub(crate) fn scan_local_headers(data: &[u8]) -> Vec<LocalEntry> {
+    let mut entries = Vec::new();
+    let mut offset = 0;
+
+    while let Some((entry, next)) = read_entry(data, offset) {
+        entries.push(entry);
+        offset = next;
+    }
+
+    entries
+}
+
+/// Read the entry whose local header starts at `offset`.
+///
+/// Returns the entry and the offset just past it.
+fn read_entry(data: &[u8], offset: usize) -> Option<(LocalEntry, usize)> {
+    let header = ZipLocalFileHeaderFixed::parse(data.get(offset..)?).ok()?;
+    let name_start = offset + ZipLocalFileHeaderFixed::SIZE;
+    let data_start = name_start + header.variable_length();
+    let raw_name = data.get(name_start..name_start + header.file_name_len as usize)?;
+
+    let path = ZipFilePath::from_bytes(raw_name);
+    let is_dir = path.is_dir();
+    let name = match path.try_normalize() {
+        Ok(normalized) => normalized.as_ref().to_string(),
+        Err(_) => String::from_utf8_lossy(raw_name).to_string(),
+    };
+    let compression_method = header.compression_method.as_method();
+
+    // Sizes are only reliable when no data descriptor follows and the entry
+    // is not a Zip64 entry, which stores its sizes in an extra field
+    let sizes_known = header.flags & DATA_DESCRIPTOR_FLAG == 0
+        && header.compressed_size != u32::MAX
+        && header.uncompressed_size != u32::MAX;
+
+    if sizes_known {
+        let data_end = data_start.checked_add(header.compressed_size as usize)?;
+        if data_end > data.len() {
+            return None;
+        }
+        let entry = LocalEntry {
+            name,
+            is_dir,
+            compression_method,
+            data: data_start..data_end,
+            verifier: ZipVerification {
+                crc: header.crc32,
+                uncompressed_size: u64::from(header.uncompressed_size),
+            },
+        };
+        return Some((entry, data_end));
+    }
+
+    let (data_end, verifier) = match compression_method {
+        CompressionMethod::Deflate => inflate_extent(data, data_start)?,
+        CompressionMethod::Store => stored_extent(data, data_start)?,
+        _ => return None,
+    };
+    let (crc, next) = skip_data_descriptor(data, data_end);
+    let entry = LocalEntry {
+        name,
+        is_dir,
+        compression_method,
+        data: data_start..data_end,
+        verifier: ZipVerification {
+            crc: crc.unwrap_or(verifier.crc),
+            uncompressed_size: verifier.uncompressed_size,
+        },
+    };
+    Some((entry, next))
+}
+
+/// Find the end of a deflate stream starting at `start` by inflating it.
+///
+/// Returns the end offset together with the CRC and size of the inflated data.
+fn inflate_extent(data: &[u8], start: usize) -> Option<(usize, ZipVerification)> {
+    let input = &data[start..];
+    let mut inflater = Decompress::new(false);
+    let mut buffer = vec![0u8; crate::RECOMMENDED_BUFFER_SIZE];
+    let mut crc = 0;
+
+    loop {
+        let consumed = inflater.total_in() as usize;
+        let produced = inflater.total_out();
+        let status = inflater
+            .decompress(&input[consumed..], &mut buffer, FlushDecompress::None)
+            .ok()?;
+        let written = (inflater.total_out() - produced) as usize;
+        crc = crc32_chunk(&buffer[..written], crc);
+
+        match status {
+            Status::StreamEnd => break,
+            // No progress means the stream was cut off
+            _ if written == 0 && inflater.total_in() as usize == consumed => return None,
+            _ => {},
+        }
+    }
+
+    let verifier = ZipVerification {
+        crc,
+        uncompressed_size: inflater.total_out(),
+    };
+    Some((start + inflater.total_in() as usize, verifier))
+}
+
+/// Find the end of stored data starting at `start` from its data descriptor.
+///
+/// The data ends at the first data descriptor signature whose recorded
+/// compressed size matches the distance from `start`.
+fn stored_extent(data: &[u8], start: usize) -> Option<(usize, ZipVerification)> {
+    let signature = DataDescriptor::SIGNATURE.to_le_bytes();
+    let mut position = start;
+
+    while let Some(found) = data
+        .get(position..)?
+        .windows(4)
+        .position(|window| window == signature)
+    {
+        let end = position + found;
+        let size = data.get(end + 8..end + 12).map(le_u32)?;
+        if size as usize == end - start {
+            let verifier = ZipVerification {
+                crc: le_u32(&data[end + 4..end + 8]),
+                uncompressed_size: u64::from(size),
+            };
+            return Some((end, verifier));
+        }
+        position = end + 1;
+    }
+
+    None
+}
+
+/// Skip the data descriptor at `offset`.
+///
+/// Returns the CRC it records, if it could be read, and the offset past it.
+fn skip_data_descriptor(data: &[u8], offset: usize) -> (Option<u32>, usize) {
+    let mut position = offset;
+    if data.get(position..position + 4).map(le_u32) == Some(DataDescriptor::SIGNATURE) {
+        position += 4;
+    }
+    let crc = data.get(position..position + 4).map(le_u32);
+
+    // The sizes take 4 bytes each, or 8 bytes each for Zip64 entries
+    for sizes_len in [8, 16] {
+        let next = position + 4 + sizes_len;
+        match data.get(next..next + 4).map(le_u32) {
+            Some(signature) if NEXT_SIGNATURES.contains(&signature) => {
+                return (crc, next);
+            },
+            // The archive ends within or right after the descriptor
+            None => return (crc, next.min(data.len())),
+            _ => {},
+        }
+    }
+
+    // Nothing recognizable follows, so this was the last readable entry
+    (crc, data.len())
+}
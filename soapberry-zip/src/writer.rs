@@ -1,8 +1,8 @@
 use crate::{
     CENTRAL_HEADER_SIGNATURE, CompressionMethod, DataDescriptor,
     END_OF_CENTRAL_DIR_LOCATOR_SIGNATURE, END_OF_CENTRAL_DIR_SIGNATURE64,
-    END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES, Error, Header, ZipFileHeaderFixed, ZipLocalFileHeaderFixed,
-    crc,
+    END_OF_CENTRAL_DIR_SIGNAUTRE_BYTES, Error, Header, ReaderAt, ZipArchive, ZipFileHeaderFixed,
+    ZipFileHeaderRecord, ZipLocalFileHeaderFixed, crc,
     errors::ErrorKind,
     extra_fields::{ExtraFieldId, ExtraFieldsContainer},
     mode::CREATOR_UNIX,
@@ -24,6 +24,9 @@ const ZIP64_THRESHOLD_FILE_SIZE: u64 = u32::MAX as u64;
 const ZIP64_THRESHOLD_OFFSET: u64 = u32::MAX as u64;
 const ZIP64_THRESHOLD_ENTRIES: usize = u16::MAX as usize;
 
+// Chunk size used when copying entries between archives
+const COPY_BUFFER_SIZE: u64 = 64 * 1024;
+
 #[derive(Debug)]
 struct CountWriter<W> {
     writer: W,
@@ -556,28 +559,37 @@ where
 
         let crc32 = crc::crc32(data);
         let size_u64 = data.len() as u64;
-        if size_u64 >= ZIP64_THRESHOLD_FILE_SIZE {
-            return Err(Error::from(ErrorKind::InvalidInput {
-                msg: "stored file too large".to_string(),
-            }));
-        }
+
+        // Sizes are known upfront, so a ZIP64 local header must carry both of
+        // them in its extra field (4.5.3)
+        let mut extra_fields = ExtraFieldsContainer::new();
+        let version_needed = if size_u64 >= ZIP64_THRESHOLD_FILE_SIZE {
+            let mut sizes = [0u8; 16];
+            sizes[..8].copy_from_slice(&size_u64.to_le_bytes());
+            sizes[8..].copy_from_slice(&size_u64.to_le_bytes());
+            extra_fields.add_field(ExtraFieldId::ZIP64, &sizes, Header::LOCAL)?;
+            ZIP64_VERSION_NEEDED
+        } else {
+            20
+        };
 
         let header = ZipLocalFileHeaderFixed {
             signature: ZipLocalFileHeaderFixed::SIGNATURE,
-            version_needed: 20,
+            version_needed,
             flags,
             compression_method: CompressionMethod::Store.as_id(),
             last_mod_time: 0,
             last_mod_date: 0,
             crc32,
-            compressed_size: size_u64 as u32,
-            uncompressed_size: size_u64 as u32,
+            compressed_size: size_u64.min(ZIP64_THRESHOLD_FILE_SIZE) as u32,
+            uncompressed_size: size_u64.min(ZIP64_THRESHOLD_FILE_SIZE) as u32,
             file_name_len: file_path.len() as u16,
-            extra_field_len: 0,
+            extra_field_len: extra_fields.local_size,
         };
 
         header.write(&mut self.writer)?;
         self.writer.write_all(file_path.as_ref().as_bytes())?;
+        extra_fields.write_extra_fields(&mut self.writer, Header::LOCAL)?;
         self.writer.write_all(data)?;
 
         let mut file_header = FileHeader {
@@ -590,7 +602,123 @@ where
             flags,
             modification_time: None,
             unix_permissions: None,
-            extra_fields: ExtraFieldsContainer::new(),
+            extra_fields,
+            copied: None,
+        };
+        file_header.finalize_extra_fields()?;
+        self.files.push(file_header);
+
+        Ok(())
+    }
+
+    /// Copies an entry from an existing archive without recompressing it.
+    ///
+    /// The local file header, compressed data and any trailing data
+    /// descriptor are copied byte-for-byte from `source`. Only the central
+    /// directory record is rebuilt so that it points at the entry's new
+    /// position; the original timestamps, attributes and extra fields are
+    /// carried over.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use std::io::Cursor;
+    /// # let mut source = rawzip::ZipArchiveWriter::new(Cursor::new(Vec::new()));
+    /// # source.write_stored_file("a.txt", b"hello")?;
+    /// # let data = source.finish()?.into_inner();
+    /// let source = rawzip::ZipArchive::from_slice(&data)?.into_zip_archive();
+    /// let mut archive = rawzip::ZipArchiveWriter::new(Cursor::new(Vec::new()));
+    /// let mut buffer = vec![0u8; rawzip::RECOMMENDED_BUFFER_SIZE];
+    /// let mut entries = source.entries(&mut buffer);
+    /// while let Some(record) = entries.next_entry()? {
+    ///     archive.copy_entry(&source, &record)?;
+    /// }
+    /// archive.finish()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn copy_entry<R>(
+        &mut self,
+        source: &ZipArchive<R>,
+        record: &ZipFileHeaderRecord<'_>,
+    ) -> Result<(), Error>
+    where
+        R: ReaderAt,
+    {
+        let file_path = record.file_path();
+        let name_bytes = file_path.as_ref();
+        if name_bytes.len() > u16::MAX as usize {
+            return Err(Error::from(ErrorKind::InvalidInput {
+                msg: "file name too long".to_string(),
+            }));
+        }
+
+        let entry = source.get_entry(record.wayfinder())?;
+        let (_, body_end) = entry.compressed_data_range();
+        let source_start = record.local_header_offset();
+        let mut source_end = body_end;
+
+        if record.has_data_descriptor() {
+            // The descriptor signature is optional and the sizes are 8 bytes
+            // wide whenever the local header announced ZIP64 (4.3.9.2)
+            let mut signature = [0u8; 4];
+            source.get_ref().read_exact_at(&mut signature, body_end)?;
+            let mut local_buffer = vec![0u8; u16::MAX as usize * 2];
+            let local_zip64 = entry
+                .local_header(&mut local_buffer)?
+                .extra_fields()
+                .any(|(id, _)| id == ExtraFieldId::ZIP64);
+            let wide = local_zip64
+                || record.compressed_size_hint() >= ZIP64_THRESHOLD_FILE_SIZE
+                || record.uncompressed_size_hint() >= ZIP64_THRESHOLD_FILE_SIZE;
+
+            let mut descriptor_len = if wide { 20 } else { 12 };
+            if u32::from_le_bytes(signature) == DataDescriptor::SIGNATURE {
+                descriptor_len += 4;
+            }
+            source_end += descriptor_len;
+        }
+
+        let local_header_offset = self.writer.count();
+        let mut buffer = vec![0u8; (source_end - source_start).min(COPY_BUFFER_SIZE) as usize];
+        let mut position = source_start;
+        while position < source_end {
+            let len = ((source_end - position) as usize).min(buffer.len());
+            source
+                .get_ref()
+                .read_exact_at(&mut buffer[..len], position)?;
+            self.writer.write_all(&buffer[..len])?;
+            position += len as u64;
+        }
+
+        // The ZIP64 field is regenerated from the new offset and sizes
+        let mut extra_fields = ExtraFieldsContainer::new();
+        for (id, data) in record.extra_fields() {
+            if id != ExtraFieldId::ZIP64 {
+                extra_fields.add_field(id, data, Header::CENTRAL)?;
+            }
+        }
+
+        self.file_names.extend_from_slice(name_bytes);
+        let (version_made_by, version_needed) = record.versions();
+        let (last_mod_time, last_mod_date) = record.dos_timestamp();
+        let mut file_header = FileHeader {
+            name_len: name_bytes.len() as u16,
+            compression_method: record.compression_method(),
+            local_header_offset,
+            compressed_size: record.compressed_size_hint(),
+            uncompressed_size: record.uncompressed_size_hint(),
+            crc: record.crc32(),
+            flags: record.flags(),
+            modification_time: None,
+            unix_permissions: None,
+            extra_fields,
+            copied: Some(CopiedFields {
+                version_made_by,
+                version_needed,
+                last_mod_time,
+                last_mod_date,
+                external_file_attrs: record.external_file_attrs(),
+            }),
         };
         file_header.finalize_extra_fields()?;
         self.files.push(file_header);
@@ -720,6 +848,7 @@ where
             modification_time: options.modification_time,
             unix_permissions: options.unix_permissions,
             extra_fields: options.extra_fields,
+            copied: None,
         };
         self.files.push(file_header);
 
@@ -810,17 +939,12 @@ where
         let central_directory_offset = self.writer.count();
         let total_entries = self.files.len();
 
-        // Determine if we need ZIP64 format
-        let needs_zip64 = total_entries >= ZIP64_THRESHOLD_ENTRIES
-            || central_directory_offset >= ZIP64_THRESHOLD_OFFSET
-            || self.files.iter().any(|f| f.needs_zip64());
-
         let mut name_offset = 0;
 
         // Write central directory entries
         for file in &self.files {
             // Version made by and version needed to extract
-            let version_needed = if file.needs_zip64() {
+            let mut version_needed = if file.needs_zip64() {
                 ZIP64_VERSION_NEEDED
             } else {
                 20
@@ -828,13 +952,24 @@ where
 
             // Set version_made_by to indicate Unix when Unix permissions are present
             let version_made_by_hi = file.unix_permissions.map(|_| CREATOR_UNIX).unwrap_or(0);
-            let version_made_by = (version_made_by_hi << 8) | version_needed;
+            let mut version_made_by = (version_made_by_hi << 8) | version_needed;
 
-            let (dos_time, dos_date) = file
+            let (mut dos_time, mut dos_date) = file
                 .modification_time
                 .as_ref()
                 .map(|dt| DosDateTime::from(dt).into_parts())
                 .unwrap_or((0, 0));
+            let mut external_file_attrs = file.unix_permissions.map(|x| x << 16).unwrap_or(0);
+
+            // Copied entries keep the metadata of their original record
+            if let Some(copied) = &file.copied {
+                version_needed = version_needed.max(copied.version_needed);
+                version_made_by = (copied.version_made_by & 0xff00)
+                    | (copied.version_made_by & 0xff).max(version_needed);
+                dos_time = copied.last_mod_time;
+                dos_date = copied.last_mod_date;
+                external_file_attrs = copied.external_file_attrs;
+            }
 
             let header = ZipFileHeaderFixed {
                 signature: CENTRAL_HEADER_SIGNATURE,
@@ -852,7 +987,7 @@ where
                 file_comment_len: 0,
                 disk_number_start: 0,
                 internal_file_attrs: 0,
-                external_file_attrs: file.unix_permissions.map(|x| x << 16).unwrap_or(0),
+                external_file_attrs,
                 local_header_offset: file.local_header_offset.min(ZIP64_THRESHOLD_OFFSET) as u32,
             };
 
@@ -872,6 +1007,12 @@ where
         let central_directory_end = self.writer.count();
         let central_directory_size = central_directory_end - central_directory_offset;
 
+        // Determine if we need ZIP64 format
+        let needs_zip64 = total_entries >= ZIP64_THRESHOLD_ENTRIES
+            || central_directory_offset >= ZIP64_THRESHOLD_OFFSET
+            || central_directory_size >= ZIP64_THRESHOLD_OFFSET
+            || self.files.iter().any(|f| f.needs_zip64());
+
         // Write ZIP64 structures if needed
         if needs_zip64 {
             let zip64_eocd_offset = self.writer.count();
@@ -999,6 +1140,7 @@ impl<'a, W> ZipEntryWriter<'a, W> {
             modification_time: self.modification_time,
             unix_permissions: self.unix_permissions,
             extra_fields: self.extra_fields,
+            copied: None,
         };
         file_header.finalize_extra_fields()?;
         self.inner.files.push(file_header);
@@ -1148,6 +1290,17 @@ struct FileHeader {
     modification_time: Option<UtcDateTime>,
     unix_permissions: Option<u32>,
     extra_fields: ExtraFieldsContainer,
+    copied: Option<CopiedFields>,
+}
+
+/// Central directory fields carried over from an entry copied verbatim.
+#[derive(Debug)]
+struct CopiedFields {
+    version_made_by: u16,
+    version_needed: u16,
+    last_mod_time: u16,
+    last_mod_date: u16,
+    external_file_attrs: u32,
 }
 
 impl FileHeader {
@@ -1257,7 +1410,8 @@ struct ZipEntryOptions {
 mod tests {
     use super::*;
     use crate::ZipArchive;
-    use std::io::Cursor;
+    use rstest::rstest;
+    use std::io::{Cursor, Read};
 
     #[test]
     fn test_name_lifetime_independence() {
@@ -1513,4 +1667,166 @@ mod tests {
         std::io::copy(&mut verifier, &mut actual).unwrap();
         assert_eq!(&actual, data);
     }
+
+    /// Serves an archive that was written at a large stream offset without
+    /// materializing the zeroed prelude that precedes it.
+    struct OffsetReader {
+        data: Vec<u8>,
+        offset: u64,
+    }
+
+    impl crate::ReaderAt for OffsetReader {
+        fn read_at(&self, buf: &mut [u8], offset: u64) -> io::Result<usize> {
+            match offset.checked_sub(self.offset) {
+                Some(pos) => self.data.read_at(buf, pos),
+                None => {
+                    let len = buf.len().min((self.offset - offset) as usize);
+                    buf[..len].fill(0);
+                    Ok(len)
+                },
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(ZIP64_THRESHOLD_OFFSET - 200)]
+    #[case(ZIP64_THRESHOLD_OFFSET - 1)]
+    #[case(ZIP64_THRESHOLD_OFFSET)]
+    #[test]
+    fn test_zip64_offsets_round_trip(#[case] offset: u64) {
+        let files: Vec<(String, Vec<u8>)> = (0..8)
+            .map(|i| {
+                let name = format!("xl/worksheets/sheet{i}.xml");
+                (name, format!("<row r=\"{i}\"/>").into_bytes())
+            })
+            .collect();
+
+        let mut archive = ZipArchiveWriterBuilder::new()
+            .with_offset(offset)
+            .build(Vec::new());
+        for (i, (name, data)) in files.iter().enumerate() {
+            if i % 2 == 0 {
+                archive.write_stored_file(name, data).unwrap();
+            } else {
+                let (mut entry, config) = archive.new_file(name).start().unwrap();
+                let mut writer = config.wrap(&mut entry);
+                writer.write_all(data).unwrap();
+                let (_, descriptor) = writer.finish().unwrap();
+                entry.finish(descriptor).unwrap();
+            }
+        }
+        let data = archive.finish().unwrap();
+        let end_offset = offset + data.len() as u64;
+
+        let mut buffer = vec![0u8; crate::RECOMMENDED_BUFFER_SIZE];
+        let archive = crate::ZipLocator::new()
+            .locate_in_reader(OffsetReader { data, offset }, &mut buffer, end_offset)
+            .map_err(|(_, e)| e)
+            .unwrap();
+
+        let mut entries = archive.entries(&mut buffer);
+        let mut found = Vec::new();
+        while let Some(entry) = entries.next_entry().unwrap() {
+            let name = entry
+                .file_path()
+                .try_normalize()
+                .unwrap()
+                .as_ref()
+                .to_string();
+            found.push((name, entry.local_header_offset(), entry.wayfinder()));
+        }
+
+        assert_eq!(found.len(), files.len());
+        assert_eq!(found[0].1, offset);
+        assert!(found[found.len() - 1].1 > ZIP64_THRESHOLD_OFFSET);
+        for ((name, _, wayfinder), (expected_name, expected)) in found.iter().zip(&files) {
+            assert_eq!(name, expected_name);
+            let entry = archive.get_entry(*wayfinder).unwrap();
+            let mut actual = Vec::new();
+            entry
+                .verifying_reader(entry.reader())
+                .read_to_end(&mut actual)
+                .unwrap();
+            assert_eq!(&actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_copy_entry_preserves_records() {
+        let mut source = ZipArchiveWriterBuilder::new()
+            .with_offset(6)
+            .build(b"PRELUD".to_vec());
+        source
+            .write_stored_file("mimetype", b"application/zip")
+            .unwrap();
+        source
+            .new_dir("docs/")
+            .unix_permissions(0o755)
+            .last_modified(UtcDateTime::from_unix(1_600_000_000))
+            .create()
+            .unwrap();
+        let (mut entry, config) = source
+            .new_file("docs/readme.txt")
+            .unix_permissions(0o600)
+            .last_modified(UtcDateTime::from_unix(1_700_000_000))
+            .start()
+            .unwrap();
+        let mut writer = config.wrap(&mut entry);
+        writer.write_all(b"copied verbatim").unwrap();
+        let (_, descriptor) = writer.finish().unwrap();
+        entry.finish(descriptor).unwrap();
+        let source_data = source.finish().unwrap();
+
+        let slice = ZipArchive::from_slice(&source_data).unwrap();
+        let records: Vec<_> = slice.entries().map(|e| e.unwrap()).collect();
+        let source = ZipArchive::from_slice(&source_data)
+            .unwrap()
+            .into_zip_archive();
+
+        let mut archive = ZipArchiveWriter::new(Vec::new());
+        archive
+            .write_stored_file("first.txt", b"shifts offsets")
+            .unwrap();
+        for record in &records {
+            archive.copy_entry(&source, record).unwrap();
+        }
+        let data = archive.finish().unwrap();
+
+        let copied = ZipArchive::from_slice(&data).unwrap();
+        let copied_records: Vec<_> = copied.entries().map(|e| e.unwrap()).collect();
+        assert_eq!(copied_records.len(), records.len() + 1);
+
+        let record_end = |records: &[ZipFileHeaderRecord<'_>], i: usize, directory: u64| {
+            records
+                .get(i + 1)
+                .map(|r| r.local_header_offset())
+                .unwrap_or(directory)
+        };
+        for (i, original) in records.iter().enumerate() {
+            let copy = &copied_records[i + 1];
+            assert_eq!(copy.file_path().as_ref(), original.file_path().as_ref());
+            assert_eq!(copy.last_modified(), original.last_modified());
+            assert_eq!(copy.mode(), original.mode());
+            assert_eq!(copy.crc32(), original.crc32());
+
+            let start = original.local_header_offset() as usize;
+            let end = record_end(&records, i, source.directory_offset()) as usize;
+            let copy_start = copy.local_header_offset() as usize;
+            let copy_end = record_end(&copied_records, i + 1, copied.directory_offset()) as usize;
+            assert_eq!(&data[copy_start..copy_end], &source_data[start..end]);
+
+            let entry = copied.get_entry(copy.wayfinder()).unwrap();
+            let mut actual = Vec::new();
+            entry
+                .verifying_reader(entry.data())
+                .read_to_end(&mut actual)
+                .unwrap();
+            let expected: &[u8] = match i {
+                0 => b"application/zip",
+                1 => b"",
+                _ => b"copied verbatim",
+            };
+            assert_eq!(actual, expected);
+        }
+    }
 }
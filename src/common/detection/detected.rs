@@ -6,6 +6,17 @@
 //!
 //! Uses SIMD-accelerated signature matching for high-performance detection.
 
+#[cfg(feature = "ole")]
+use crate::common::detection::FileFormat;
+#[cfg(feature = "ole")]
+use crate::ole::{OleFile, OleSource};
+#[cfg(feature = "ole")]
+use std::fs::File;
+#[cfg(feature = "ole")]
+use std::io::{BufReader, Read, Seek};
+#[cfg(feature = "ole")]
+use std::path::Path;
+
 /// Detected format with pre-parsed data structures for all formats.
 ///
 /// This enum represents the result of format detection, where each format
@@ -62,6 +73,35 @@ pub enum DetectedFormat {
     // RTF format (plain text, no parsing structure needed)
     #[cfg(feature = "rtf")]
     Rtf(Vec<u8>),
+
+    // Encrypted OOXML package stored in an OLE container (EncryptedPackage stream)
+    #[cfg(feature = "ole")]
+    Encrypted(crate::ole::OleFile<std::io::Cursor<Vec<u8>>>),
+}
+
+/// Options controlling how a file is opened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OpenOptions {
+    /// Whether damaged OOXML packages are repaired instead of rejected
+    ///
+    /// A package whose ZIP central directory is unreadable, for example
+    /// because the file was truncated, is read by scanning local file
+    /// headers, and missing or malformed content types are inferred from
+    /// part names. See [`OpcPackage::recover`](crate::ooxml::OpcPackage::recover).
+    pub recovery: bool,
+}
+
+impl OpenOptions {
+    /// Create options that open files strictly.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether damaged OOXML packages are repaired instead of rejected.
+    pub fn with_recovery(mut self, recovery: bool) -> Self {
+        self.recovery = recovery;
+        self
+    }
 }
 
 /// Smart single-pass format detection with pre-parsed data structures.
@@ -92,13 +132,36 @@ pub enum DetectedFormat {
 /// * `Some(DetectedFormat)` - Format detected with pre-parsed structure
 /// * `None` - Format not recognized
 pub fn detect_format_smart(bytes: Vec<u8>) -> Option<DetectedFormat> {
+    detect_format_checked(bytes).ok()
+}
+
+/// Detect the format like [`detect_format_smart`], reporting why it failed.
+///
+/// Files that are not Office documents at all fail with
+/// [`Error::NotOfficeFile`](crate::common::Error::NotOfficeFile). A damaged
+/// container fails with the error that stopped it from opening instead, such
+/// as [`Error::Zip`](crate::common::Error::Zip) for a truncated archive or
+/// [`Error::MissingPart`](crate::common::Error::MissingPart) for a package
+/// without its main part.
+pub(crate) fn detect_format_checked(bytes: Vec<u8>) -> crate::common::Result<DetectedFormat> {
+    detect_format_with_options(bytes, &OpenOptions::default())
+}
+
+/// Detect the format like [`detect_format_checked`], opening the file as
+/// `options` asks.
+#[cfg_attr(not(feature = "ooxml"), allow(unused_variables))] // Options only affect OOXML packages
+pub(crate) fn detect_format_with_options(
+    bytes: Vec<u8>,
+    options: &OpenOptions,
+) -> crate::common::Result<DetectedFormat> {
+    use crate::common::Error;
     #[cfg(any(feature = "ooxml", feature = "iwa", feature = "odf"))]
     use crate::common::detection::FileFormat;
     use crate::common::detection::simd_utils::check_office_signatures;
 
     // Quick signature checks (first 4-8 bytes)
     if bytes.len() < 8 {
-        return None;
+        return Err(Error::NotOfficeFile);
     }
 
     // Use parallel signature checking to test OLE2, ZIP, and RTF simultaneously
@@ -107,47 +170,62 @@ pub fn detect_format_smart(bytes: Vec<u8>) -> Option<DetectedFormat> {
 
     // Check RTF first (simplest check, no parsing needed)
     #[cfg(feature = "rtf")]
-    if mask.is_rtf() {
-        return Some(DetectedFormat::Rtf(bytes));
+    if mask.is_rtf() || crate::common::detection::rtf::detect_rtf_format(&bytes).is_some() {
+        return Ok(DetectedFormat::Rtf(bytes));
     }
 
     // Check OLE2 signature (DOC, PPT, XLS) - parse OleFile once
     #[cfg(feature = "ole")]
     if mask.is_ole2() {
         let cursor = std::io::Cursor::new(bytes);
-        if let Ok(ole_file) = crate::ole::OleFile::open(cursor) {
-            // Use existing OLE2 detection logic by checking streams
-            if ole_file.exists(&["WordDocument"]) {
-                return Some(DetectedFormat::Doc(ole_file));
-            }
-            if ole_file.exists(&["PowerPoint Document"]) || ole_file.exists(&["Current User"]) {
-                return Some(DetectedFormat::Ppt(ole_file));
-            }
-            if ole_file.exists(&["Workbook"]) || ole_file.exists(&["Book"]) {
-                return Some(DetectedFormat::Xls(ole_file));
-            }
+        let ole_file = crate::ole::OleFile::open(cursor)?;
+        // Password-protected OOXML files are OLE containers around the package
+        if is_encrypted_package(&ole_file) {
+            return Ok(DetectedFormat::Encrypted(ole_file));
         }
-        return None;
+        return match legacy_format(&ole_file) {
+            Some(FileFormat::Doc) => Ok(DetectedFormat::Doc(ole_file)),
+            Some(FileFormat::Ppt) => Ok(DetectedFormat::Ppt(ole_file)),
+            Some(_) => Ok(DetectedFormat::Xls(ole_file)),
+            None => Err(Error::NotOfficeFile),
+        };
     }
 
     // Check ZIP signature (OOXML, iWork, ODF) - parse once and determine type
     if mask.is_zip() {
+        // Why the archive failed to open as an OOXML package, reported if it
+        // turns out not to be iWork or ODF either
+        #[cfg(feature = "ooxml")]
+        let mut package_error = None;
+
         // Try to parse as OPC package (OOXML) first - single parse!
         #[cfg(feature = "ooxml")]
         {
-            if let Ok(package) = crate::ooxml::OpcPackage::from_bytes(&bytes) {
-                // Use existing OOXML detection logic
-                if let Some(format) =
-                    crate::common::detection::ooxml::detect_ooxml_format_from_package(&package)
-                {
-                    return match format {
-                        FileFormat::Docx => Some(DetectedFormat::Docx(package)),
-                        FileFormat::Pptx => Some(DetectedFormat::Pptx(package)),
-                        FileFormat::Xlsx => Some(DetectedFormat::Xlsx(package)),
-                        FileFormat::Xlsb => Some(DetectedFormat::Xlsb(package)),
-                        _ => None,
-                    };
-                }
+            use crate::ooxml::opc::error::OpcError;
+
+            let package = if options.recovery {
+                crate::ooxml::OpcPackage::recover(&bytes)
+            } else {
+                crate::ooxml::OpcPackage::from_bytes(&bytes)
+            };
+            match package {
+                Ok(package) => {
+                    // Use existing OOXML detection logic
+                    if let Some(format) =
+                        crate::common::detection::ooxml::detect_ooxml_format_from_package(&package)
+                    {
+                        return match format {
+                            FileFormat::Docx => Ok(DetectedFormat::Docx(package)),
+                            FileFormat::Pptx => Ok(DetectedFormat::Pptx(package)),
+                            FileFormat::Xlsx => Ok(DetectedFormat::Xlsx(package)),
+                            FileFormat::Xlsb => Ok(DetectedFormat::Xlsb(package)),
+                            _ => Err(Error::NotOfficeFile),
+                        };
+                    }
+                },
+                // No [Content_Types].xml, so this is not an OOXML package
+                Err(OpcError::PartNotFound(partname)) if partname == "[Content_Types].xml" => {},
+                Err(err) => package_error = Some(Error::from(err)),
             }
         }
 
@@ -164,10 +242,10 @@ pub fn detect_format_smart(bytes: Vec<u8>) -> Option<DetectedFormat> {
                         crate::common::detection::iwork::detect_iwork_format(&archive)
                     {
                         return match format {
-                            FileFormat::Keynote => Some(DetectedFormat::Keynote(bytes)),
-                            FileFormat::Pages => Some(DetectedFormat::Pages(bytes)),
-                            FileFormat::Numbers => Some(DetectedFormat::Numbers(bytes)),
-                            _ => None,
+                            FileFormat::Keynote => Ok(DetectedFormat::Keynote(bytes)),
+                            FileFormat::Pages => Ok(DetectedFormat::Pages(bytes)),
+                            FileFormat::Numbers => Ok(DetectedFormat::Numbers(bytes)),
+                            _ => Err(Error::NotOfficeFile),
                         };
                     }
                 }
@@ -184,17 +262,176 @@ pub fn detect_format_smart(bytes: Vec<u8>) -> Option<DetectedFormat> {
                             )
                         {
                             return match format {
-                                FileFormat::Odt => Some(DetectedFormat::Odt(bytes)),
-                                FileFormat::Odp => Some(DetectedFormat::Odp(bytes)),
-                                FileFormat::Ods => Some(DetectedFormat::Ods(bytes)),
-                                _ => None,
+                                FileFormat::Odt => Ok(DetectedFormat::Odt(bytes)),
+                                FileFormat::Odp => Ok(DetectedFormat::Odp(bytes)),
+                                FileFormat::Ods => Ok(DetectedFormat::Ods(bytes)),
+                                _ => Err(Error::NotOfficeFile),
                             };
                         }
                     }
                 }
             }
         }
+
+        #[cfg(feature = "ooxml")]
+        if let Some(err) = package_error {
+            return Err(err);
+        }
     }
 
-    None
+    Err(Error::NotOfficeFile)
+}
+
+/// Detect the format of a file that may be password-protected.
+///
+/// Encrypted OOXML packages are decrypted in memory with `password` and the
+/// decrypted package is detected again. Unencrypted files are detected as usual,
+/// so the password is simply ignored for them.
+#[cfg(feature = "ooxml_encryption")]
+pub(crate) fn detect_format_with_password(
+    bytes: Vec<u8>,
+    password: &str,
+) -> crate::common::Result<DetectedFormat> {
+    use crate::common::Error;
+
+    match detect_format_checked(bytes)? {
+        DetectedFormat::Encrypted(mut ole) => {
+            let decrypted = crate::ooxml::crypto::decrypt_ooxml_ole_file(&mut ole, password)?;
+            match detect_format_smart(decrypted.package_bytes) {
+                Some(DetectedFormat::Encrypted(_)) | None => Err(Error::InvalidFormat(
+                    "decrypted package is not a supported OOXML file".to_string(),
+                )),
+                Some(detected) => Ok(detected),
+            }
+        },
+        detected => Ok(detected),
+    }
+}
+
+/// Whether an OLE container holds a password-protected OOXML package.
+#[cfg(feature = "ole")]
+fn is_encrypted_package<R: Read + Seek>(ole_file: &OleFile<R>) -> bool {
+    ole_file.exists(&["EncryptedPackage"]) && ole_file.exists(&["EncryptionInfo"])
+}
+
+/// Identify the legacy binary format of an OLE container by its streams.
+#[cfg(feature = "ole")]
+fn legacy_format<R: Read + Seek>(ole_file: &OleFile<R>) -> Option<FileFormat> {
+    if ole_file.exists(&["WordDocument"]) {
+        Some(FileFormat::Doc)
+    } else if ole_file.exists(&["PowerPoint Document"]) || ole_file.exists(&["Current User"]) {
+        Some(FileFormat::Ppt)
+    } else if ole_file.exists(&["Workbook"]) || ole_file.exists(&["Book"]) {
+        Some(FileFormat::Xls)
+    } else {
+        None
+    }
+}
+
+/// Open a legacy binary file of the given `format` so that it is read from
+/// disk on demand instead of being loaded into memory.
+///
+/// Returns `None` when the file at `path` is anything else, including a
+/// damaged OLE container; callers then read it in full and detect it with
+/// [`detect_format_checked`], which reports the error. Password-protected
+/// files fail like in [`check_legacy_encryption`].
+#[cfg(feature = "ole")]
+pub(crate) fn open_legacy_file(
+    path: &Path,
+    format: FileFormat,
+) -> crate::common::Result<Option<OleFile<OleSource>>> {
+    let mut file = File::open(path)?;
+    let mut signature = [0u8; 8];
+    if file.read_exact(&mut signature).is_err()
+        || !crate::common::detection::simd_utils::check_office_signatures(&signature).is_ole2()
+    {
+        return Ok(None);
+    }
+    file.rewind()?;
+
+    let Ok(mut ole_file) = OleFile::open(OleSource::File(BufReader::new(file))) else {
+        return Ok(None);
+    };
+    if is_encrypted_package(&ole_file) || legacy_format(&ole_file) != Some(format) {
+        return Ok(None);
+    }
+    if is_legacy_encrypted(format, &mut ole_file) {
+        return Err(legacy_encryption_error());
+    }
+    Ok(Some(ole_file))
+}
+
+/// Reject legacy binary files that were saved with a password.
+///
+/// Word sets `fEncrypted` in the FIB, Excel writes a FILEPASS record before the
+/// sheet list and PowerPoint stores an `EncryptedSummary` stream. These use
+/// RC4 or XOR obfuscation, which is not supported, so they are reported as
+/// [`Error::Encrypted`](crate::common::Error::Encrypted) instead of failing later
+/// with a confusing parse error.
+#[cfg(feature = "ole")]
+pub(crate) fn check_legacy_encryption(detected: &mut DetectedFormat) -> crate::common::Result<()> {
+    let encrypted = match detected {
+        DetectedFormat::Doc(ole) => is_legacy_encrypted(FileFormat::Doc, ole),
+        DetectedFormat::Ppt(ole) => is_legacy_encrypted(FileFormat::Ppt, ole),
+        DetectedFormat::Xls(ole) => is_legacy_encrypted(FileFormat::Xls, ole),
+        #[allow(unreachable_patterns)]
+        _ => false,
+    };
+
+    if encrypted {
+        return Err(legacy_encryption_error());
+    }
+    Ok(())
+}
+
+/// Whether a legacy binary file of `format` was saved with a password.
+///
+/// See [`check_legacy_encryption`].
+#[cfg(feature = "ole")]
+fn is_legacy_encrypted<R: Read + Seek>(format: FileFormat, ole: &mut OleFile<R>) -> bool {
+    /// Offset of the FIB flags holding `fEncrypted`
+    const FIB_FLAGS: u64 = 0x0A;
+    /// FIB flag bit marking an encrypted Word document
+    const FIB_ENCRYPTED: u16 = 0x0100;
+    /// BIFF record ids
+    const FILEPASS: u16 = 0x002F;
+    const BOUNDSHEET: u16 = 0x0085;
+
+    match format {
+        FileFormat::Doc => {
+            // Only the flags are read, since the stream holds the whole text
+            let mut flags = [0u8; 2];
+            ole.stream_location(&["WordDocument"])
+                .and_then(|location| ole.read_stream_range(&location, FIB_FLAGS, &mut flags))
+                .is_ok_and(|read| {
+                    read == flags.len() && u16::from_le_bytes(flags) & FIB_ENCRYPTED != 0
+                })
+        },
+        FileFormat::Ppt => ole.exists(&["EncryptedSummary"]),
+        FileFormat::Xls => {
+            let stream = ole
+                .open_stream(&["Workbook"])
+                .or_else(|_| ole.open_stream(&["Book"]))
+                .unwrap_or_default();
+            let mut pos = 0;
+            while let Some(header) = stream.get(pos..pos + 4) {
+                let id = u16::from_le_bytes([header[0], header[1]]);
+                if id == FILEPASS {
+                    return true;
+                }
+                if id == BOUNDSHEET {
+                    break;
+                }
+                pos += 4 + usize::from(u16::from_le_bytes([header[2], header[3]]));
+            }
+            false
+        },
+        _ => false,
+    }
+}
+
+/// Error for a legacy binary file that was saved with a password
+#[cfg(feature = "ole")]
+fn legacy_encryption_error() -> crate::common::Error {
+    crate::common::Error::Encrypted("password-protected legacy Office file".to_string())
 }
@@ -119,10 +119,8 @@ pub fn detect_file_format_from_bytes(bytes: &[u8]) -> Option<FileFormat> {
         return None;
     }
 
-    // Check RTF format (if matched)
-    if mask.is_rtf()
-        && let Some(result) = rtf::detect_rtf_format(bytes)
-    {
+    // Check RTF format, including BOM-prefixed and compressed RTF the mask misses
+    if let Some(result) = rtf::detect_rtf_format(bytes) {
         return Some(result);
     }
 
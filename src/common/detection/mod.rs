@@ -19,7 +19,11 @@ pub mod types;
 pub mod utils;
 
 // Re-exports
-pub use detected::{DetectedFormat, detect_format_smart};
+#[cfg(feature = "ole")]
+pub(crate) use detected::{check_legacy_encryption, open_legacy_file};
+#[cfg(feature = "ooxml_encryption")]
+pub(crate) use detected::detect_format_with_password;
+pub use detected::{DetectedFormat, OpenOptions, detect_format_smart};
 pub use functions::{
     detect_file_format, detect_file_format_from_bytes, detect_format_from_reader,
     detect_iwork_format_from_path,
@@ -1,7 +1,9 @@
 //! RTF (Rich Text Format) detection.
 //!
 //! RTF files have a simple text-based signature that makes them easy to detect.
-//! The signature is `{\rtf` or `{\rtf1` at the start of the file.
+//! The signature is `{\rtf` or `{\rtf1` at the start of the file, optionally
+//! preceded by a UTF-8 byte order mark. Compressed RTF (as stored by Outlook)
+//! is recognised by the `LZFu`/`MELA` tag in its 16-byte header.
 //!
 //! Uses SIMD-accelerated signature matching for improved performance.
 
@@ -14,11 +16,19 @@ use std::io::{Read, Seek};
 const RTF_SIGNATURE_MIN: &[u8] = b"{\\rtf";
 const RTF_SIGNATURE_LEN: usize = 5;
 
+/// UTF-8 byte order mark some editors write before the RTF signature.
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Compressed RTF header tags at offset 8 (compressed and stored variants).
+const COMPRESSED_RTF_TAGS: [&[u8]; 2] = [b"LZFu", b"MELA"];
+const COMPRESSED_RTF_HEADER_LEN: usize = 16;
+
 /// Detect RTF format from byte content.
 ///
 /// RTF files are plain text files that start with `{\rtf` or `{\rtf1`.
 /// This function checks the first few bytes for this signature using
-/// SIMD-accelerated comparison when available.
+/// SIMD-accelerated comparison when available. Compressed RTF is reported as
+/// [`FileFormat::Rtf`] as well, since the RTF parser decompresses it.
 ///
 /// # Arguments
 ///
@@ -52,13 +62,24 @@ pub fn detect_rtf_format(bytes: &[u8]) -> Option<FileFormat> {
     }
 
     // Check for RTF signature using SIMD-accelerated matching
-    if signature_matches(bytes, RTF_SIGNATURE_MIN) {
+    let plain = bytes.strip_prefix(UTF8_BOM).unwrap_or(bytes);
+    if plain.len() >= RTF_SIGNATURE_LEN && signature_matches(plain, RTF_SIGNATURE_MIN) {
+        return Some(FileFormat::Rtf);
+    }
+
+    if is_compressed_rtf(bytes) {
         return Some(FileFormat::Rtf);
     }
 
     None
 }
 
+/// Check for the header of a compressed RTF stream.
+#[inline]
+pub fn is_compressed_rtf(bytes: &[u8]) -> bool {
+    bytes.len() >= COMPRESSED_RTF_HEADER_LEN && COMPRESSED_RTF_TAGS.contains(&&bytes[8..12])
+}
+
 /// Detect RTF format from a reader.
 ///
 /// This function reads the first few bytes from the reader to check
@@ -75,9 +96,14 @@ pub fn detect_rtf_format(bytes: &[u8]) -> Option<FileFormat> {
 pub fn detect_rtf_format_from_reader<R: Read + Seek>(reader: &mut R) -> Option<FileFormat> {
     use std::io::SeekFrom;
 
-    // Read first bytes for signature check
-    let mut buffer = [0u8; RTF_SIGNATURE_LEN];
-    if reader.read_exact(&mut buffer).is_err() {
+    // Read first bytes for signature check (enough for the compressed header)
+    let mut buffer = Vec::with_capacity(COMPRESSED_RTF_HEADER_LEN);
+    if reader
+        .by_ref()
+        .take(COMPRESSED_RTF_HEADER_LEN as u64)
+        .read_to_end(&mut buffer)
+        .is_err()
+    {
         return None;
     }
 
@@ -130,4 +156,27 @@ mod tests {
         cursor.read_to_end(&mut buffer).unwrap();
         assert_eq!(&buffer[..], rtf_data);
     }
+
+    #[test]
+    fn test_detect_rtf_with_bom() {
+        let rtf_data = b"\xEF\xBB\xBF{\\rtf1\\ansi Hello}";
+        assert_eq!(detect_rtf_format(rtf_data), Some(FileFormat::Rtf));
+    }
+
+    #[test]
+    fn test_detect_compressed_rtf() {
+        let mut header = Vec::new();
+        header.extend_from_slice(&12u32.to_le_bytes());
+        header.extend_from_slice(&0u32.to_le_bytes());
+        header.extend_from_slice(b"LZFu");
+        header.extend_from_slice(&0u32.to_le_bytes());
+        assert_eq!(detect_rtf_format(&header), Some(FileFormat::Rtf));
+
+        let mut cursor = Cursor::new(header);
+        assert_eq!(
+            detect_rtf_format_from_reader(&mut cursor),
+            Some(FileFormat::Rtf)
+        );
+        assert!(!is_compressed_rtf(b"{\\rtf1\\ansi Hello World}"));
+    }
 }
@@ -202,6 +202,180 @@ pub fn decode_bytes(bytes: &[u8], codepage: Option<u32>) -> Option<String> {
     Some(encoding.decode(bytes).0.into_owned())
 }
 
+/// Map a Windows language identifier (LID/LCID) to its ANSI codepage.
+///
+/// Legacy Office formats store 8-bit text in the ANSI codepage of the
+/// document language. Unknown languages fall back to Windows-1252.
+///
+/// # Examples
+///
+/// ```
+/// use litchi::common::encoding::lid_to_codepage;
+///
+/// assert_eq!(lid_to_codepage(0x0419), 1251); // Russian
+/// assert_eq!(lid_to_codepage(0x0804), 936); // Chinese (PRC)
+/// assert_eq!(lid_to_codepage(0x0409), 1252); // English (US)
+/// ```
+pub fn lid_to_codepage(lid: u16) -> u32 {
+    // The primary language is in the low 10 bits
+    match lid & 0x03FF {
+        // Cyrillic: Bulgarian, Russian, Ukrainian, Belarusian, Macedonian
+        0x02 | 0x19 | 0x22 | 0x23 | 0x2F => 1251,
+        // Serbian (Cyrillic) shares its primary language with Croatian
+        0x1A if matches!(lid, 0x0C1A | 0x1C1A) => 1251,
+        // Central European: Czech, Hungarian, Polish, Romanian, Croatian,
+        // Slovak, Albanian, Slovenian
+        0x05 | 0x0E | 0x15 | 0x18 | 0x1A | 0x1B | 0x1C | 0x24 => 1250,
+        0x08 => 1253,               // Greek
+        0x1F | 0x2C => 1254,        // Turkish, Azeri
+        0x0D => 1255,               // Hebrew
+        0x01 | 0x20 | 0x29 => 1256, // Arabic, Urdu, Farsi
+        0x25..=0x27 => 1257,        // Estonian, Latvian, Lithuanian
+        0x2A => 1258,               // Vietnamese
+        0x1E => 874,                // Thai
+        0x11 => 932,                // Japanese
+        0x12 => 949,                // Korean
+        // Chinese: Taiwan, Hong Kong and Macau use Big5
+        0x04 if matches!(lid, 0x0404 | 0x0C04 | 0x1404) => 950,
+        0x04 => 936,
+        _ => 1252,
+    }
+}
+
+/// Map a font character set (`lfCharSet`) to its codepage.
+///
+/// Returns `None` for `DEFAULT_CHARSET` (1) and `SYMBOL_CHARSET` (2), whose
+/// text is not in a fixed codepage.
+///
+/// # Examples
+///
+/// ```
+/// use litchi::common::encoding::charset_to_codepage;
+///
+/// assert_eq!(charset_to_codepage(204), Some(1251)); // RUSSIAN_CHARSET
+/// assert_eq!(charset_to_codepage(2), None); // SYMBOL_CHARSET
+/// ```
+pub fn charset_to_codepage(charset: u8) -> Option<u32> {
+    Some(match charset {
+        0 => 1252,   // ANSI_CHARSET
+        77 => 10000, // MAC_CHARSET
+        128 => 932,  // SHIFTJIS_CHARSET
+        129 => 949,  // HANGUL_CHARSET
+        134 => 936,  // GB2312_CHARSET
+        136 => 950,  // CHINESEBIG5_CHARSET
+        161 => 1253, // GREEK_CHARSET
+        162 => 1254, // TURKISH_CHARSET
+        163 => 1258, // VIETNAMESE_CHARSET
+        177 => 1255, // HEBREW_CHARSET
+        178 => 1256, // ARABIC_CHARSET
+        186 => 1257, // BALTIC_CHARSET
+        204 => 1251, // RUSSIAN_CHARSET
+        222 => 874,  // THAI_CHARSET
+        238 => 1250, // EASTEUROPE_CHARSET
+        255 => 437,  // OEM_CHARSET
+        _ => return None,
+    })
+}
+
+/// Check whether a codepage uses lead and trail bytes for some characters.
+#[inline]
+pub fn is_double_byte_codepage(codepage: u32) -> bool {
+    matches!(codepage, 932 | 936 | 949 | 950 | 20932 | 54936)
+}
+
+/// Map a character of a symbol font to its Unicode equivalent.
+///
+/// Symbol fonts (Symbol, Wingdings) draw their own glyphs at ASCII code
+/// points, and Office stores them either as the raw code or shifted into the
+/// private use area (U+F020-U+F0FF). Returns `None` if the font is not a
+/// known symbol font or the code has no standard Unicode mapping.
+///
+/// # Examples
+///
+/// ```
+/// use litchi::common::encoding::symbol_font_char;
+///
+/// assert_eq!(symbol_font_char("Symbol", '\u{F0B7}'), Some('•'));
+/// assert_eq!(symbol_font_char("Symbol", 'a'), Some('α'));
+/// assert_eq!(symbol_font_char("Wingdings", '\u{F0FC}'), Some('✓'));
+/// assert_eq!(symbol_font_char("Arial", 'a'), None);
+/// ```
+pub fn symbol_font_char(font_name: &str, ch: char) -> Option<char> {
+    let code = match ch as u32 {
+        code @ 0xF020..=0xF0FF => code - 0xF000,
+        code @ 0x20..=0xFF => code,
+        _ => return None,
+    } as u8;
+
+    let mapped = if font_name.eq_ignore_ascii_case("Symbol") {
+        match code {
+            0x20..=0x7E => SYMBOL_LOW[(code - 0x20) as usize],
+            0xA0..=0xFE => SYMBOL_HIGH[(code - 0xA0) as usize],
+            _ => '\0',
+        }
+    } else if font_name.eq_ignore_ascii_case("Wingdings") {
+        wingdings_char(code)
+    } else {
+        return None;
+    };
+    (mapped != '\0').then_some(mapped)
+}
+
+/// Symbol font, 0x20-0x7E
+const SYMBOL_LOW: [char; 95] = [
+    ' ', '!', '∀', '#', '∃', '%', '&', '∋', '(', ')', '∗', '+', ',', '−', '.', '/', '0', '1', '2',
+    '3', '4', '5', '6', '7', '8', '9', ':', ';', '<', '=', '>', '?', '≅', 'Α', 'Β', 'Χ', 'Δ', 'Ε',
+    'Φ', 'Γ', 'Η', 'Ι', 'ϑ', 'Κ', 'Λ', 'Μ', 'Ν', 'Ο', 'Π', 'Θ', 'Ρ', 'Σ', 'Τ', 'Υ', 'ς', 'Ω', 'Ξ',
+    'Ψ', 'Ζ', '[', '∴', ']', '⊥', '_', '‾', 'α', 'β', 'χ', 'δ', 'ε', 'φ', 'γ', 'η', 'ι', 'ϕ', 'κ',
+    'λ', 'μ', 'ν', 'ο', 'π', 'θ', 'ρ', 'σ', 'τ', 'υ', 'ϖ', 'ω', 'ξ', 'ψ', 'ζ', '{', '|', '}', '∼',
+];
+
+/// Symbol font, 0xA0-0xFE (0xF0 has no Unicode equivalent)
+const SYMBOL_HIGH: [char; 95] = [
+    '€', 'ϒ', '′', '≤', '⁄', '∞', 'ƒ', '♣', '♦', '♥', '♠', '↔', '←', '↑', '→', '↓', '°', '±', '″',
+    '≥', '×', '∝', '∂', '•', '÷', '≠', '≡', '≈', '…', '⏐', '⎯', '↵', 'ℵ', 'ℑ', 'ℜ', '℘', '⊗', '⊕',
+    '∅', '∩', '∪', '⊃', '⊇', '⊄', '⊂', '⊆', '∈', '∉', '∠', '∇', '®', '©', '™', '∏', '√', '⋅', '¬',
+    '∧', '∨', '⇔', '⇐', '⇑', '⇒', '⇓', '◊', '〈', '®', '©', '™', '∑', '⎛', '⎜', '⎝', '⎡', '⎢', '⎣',
+    '⎧', '⎨', '⎩', '⎪', '\0', '〉', '∫', '⌠', '⎮', '⌡', '⎞', '⎟', '⎠', '⎤', '⎥', '⎦', '⎫', '⎬',
+    '⎭',
+];
+
+/// Wingdings characters with a standard Unicode equivalent, mostly the
+/// bullets and check marks used in lists
+fn wingdings_char(code: u8) -> char {
+    match code {
+        0x22 => '✂',
+        0x28 => '☎',
+        0x2A => '✉',
+        0x46 => '☞',
+        0x4A => '☺',
+        0x4C => '☹',
+        0x4E => '☠',
+        0x54 => '❄',
+        0x6C => '●',
+        0x6E => '■',
+        0x6F => '□',
+        0x71 => '❑',
+        0x72 => '❒',
+        0x75 => '◆',
+        0x76 => '❖',
+        0x77 => '⬥',
+        0x9F => '•',
+        0xA1 => '○',
+        0xA7 => '▪',
+        0xA8 => '◻',
+        0xAB => '★',
+        0xD8 => '➢',
+        0xE8 => '➔',
+        0xF0 => '⇨',
+        0xFB => '✗',
+        0xFC => '✓',
+        0xFD => '☒',
+        0xFE => '☑',
+        _ => '\0',
+    }
+}
+
 /// Decode hex-encoded string to bytes with SIMD acceleration.
 ///
 /// This function efficiently decodes hex-encoded strings into byte vectors using
@@ -781,6 +955,35 @@ mod tests {
         assert!(codepage_to_encoding(99999).is_none());
     }
 
+    #[test]
+    fn test_lid_and_charset_codepages() {
+        assert_eq!(lid_to_codepage(0x0419), 1251); // Russian
+        assert_eq!(lid_to_codepage(0x0C1A), 1251); // Serbian (Cyrillic)
+        assert_eq!(lid_to_codepage(0x041A), 1250); // Croatian
+        assert_eq!(lid_to_codepage(0x0411), 932); // Japanese
+        assert_eq!(lid_to_codepage(0x0412), 949); // Korean
+        assert_eq!(lid_to_codepage(0x0804), 936); // Chinese (PRC)
+        assert_eq!(lid_to_codepage(0x0404), 950); // Chinese (Taiwan)
+        assert_eq!(lid_to_codepage(0x0407), 1252); // German
+        assert_eq!(charset_to_codepage(128), Some(932));
+        assert_eq!(charset_to_codepage(238), Some(1250));
+        assert_eq!(charset_to_codepage(1), None);
+    }
+
+    #[test]
+    fn test_symbol_font_char() {
+        assert_eq!(symbol_font_char("Symbol", '\u{F0B7}'), Some('•'));
+        assert_eq!(symbol_font_char("symbol", '\u{00B7}'), Some('•'));
+        assert_eq!(symbol_font_char("Symbol", 'p'), Some('π'));
+        assert_eq!(symbol_font_char("Symbol", 'S'), Some('Σ'));
+        assert_eq!(symbol_font_char("Symbol", '\u{F0A5}'), Some('∞'));
+        assert_eq!(symbol_font_char("Symbol", '\u{F0F0}'), None);
+        assert_eq!(symbol_font_char("Wingdings", '\u{F0A7}'), Some('▪'));
+        assert_eq!(symbol_font_char("Wingdings", '\u{F0D8}'), Some('➢'));
+        assert_eq!(symbol_font_char("Wingdings", 'A'), None);
+        assert_eq!(symbol_font_char("Times New Roman", '\u{F0B7}'), None);
+    }
+
     #[test]
     fn test_decode_utf16le() {
         let bytes = b"H\x00e\x00l\x00l\x00o\x00";
@@ -22,8 +22,9 @@ impl From<crate::ole::OleError> for Error {
             crate::ole::OleError::InvalidData(s) => Error::InvalidFormat(s),
             crate::ole::OleError::NotOleFile => Error::NotOfficeFile,
             crate::ole::OleError::CorruptedFile(s) => Error::CorruptedFile(s),
+            crate::ole::OleError::ParseError(s) => Error::ParseError(s),
             crate::ole::OleError::StreamNotFound => {
-                Error::ComponentNotFound("Stream not found".to_string())
+                Error::MissingPart("Stream not found".to_string())
             },
         }
     }
@@ -36,7 +37,7 @@ impl From<crate::ole::doc::package::DocError> for Error {
             crate::ole::doc::package::DocError::Io(e) => Error::Io(e),
             crate::ole::doc::package::DocError::Ole(ole_err) => Error::from(ole_err),
             crate::ole::doc::package::DocError::InvalidFormat(s) => Error::InvalidFormat(s),
-            crate::ole::doc::package::DocError::StreamNotFound(s) => Error::ComponentNotFound(s),
+            crate::ole::doc::package::DocError::StreamNotFound(s) => Error::MissingPart(s),
             crate::ole::doc::package::DocError::Corrupted(s) => Error::CorruptedFile(s),
         }
     }
@@ -49,7 +50,7 @@ impl From<crate::ole::ppt::package::PptError> for Error {
             crate::ole::ppt::package::PptError::Io(e) => Error::Io(e),
             crate::ole::ppt::package::PptError::Ole(ole_err) => Error::from(ole_err),
             crate::ole::ppt::package::PptError::InvalidFormat(s) => Error::InvalidFormat(s),
-            crate::ole::ppt::package::PptError::StreamNotFound(s) => Error::ComponentNotFound(s),
+            crate::ole::ppt::package::PptError::StreamNotFound(s) => Error::MissingPart(s),
             crate::ole::ppt::package::PptError::Corrupted(s) => Error::CorruptedFile(s),
         }
     }
@@ -67,8 +68,13 @@ impl From<crate::ooxml::error::OoxmlError> for Error {
     fn from(err: crate::ooxml::error::OoxmlError) -> Self {
         match err {
             crate::ooxml::error::OoxmlError::Io(e) => Error::Io(e),
-            crate::ooxml::error::OoxmlError::Xml(s) => Error::XmlError(s),
-            crate::ooxml::error::OoxmlError::PartNotFound(s) => Error::ComponentNotFound(s),
+            crate::ooxml::error::OoxmlError::Xml(s) => Error::xml(s),
+            crate::ooxml::error::OoxmlError::MalformedXml { part, line, source } => Error::Xml {
+                part: Some(part),
+                line: Some(line),
+                source: Box::new(source),
+            },
+            crate::ooxml::error::OoxmlError::PartNotFound(s) => Error::MissingPart(s),
             crate::ooxml::error::OoxmlError::InvalidContentType { expected, got } => {
                 Error::InvalidContentType { expected, got }
             },
@@ -77,6 +83,10 @@ impl From<crate::ooxml::error::OoxmlError> for Error {
             crate::ooxml::error::OoxmlError::Opc(e) => Error::from_opc_error(e),
             crate::ooxml::error::OoxmlError::IoError(e) => Error::Io(e),
             crate::ooxml::error::OoxmlError::InvalidUri(s) => Error::Other(s),
+            crate::ooxml::error::OoxmlError::IncorrectPassword(s) => Error::IncorrectPassword(s),
+            crate::ooxml::error::OoxmlError::UnsupportedEncryption(s) => {
+                Error::UnsupportedEncryption(s)
+            },
             crate::ooxml::error::OoxmlError::Other(s) => Error::Other(s),
         }
     }
@@ -85,11 +95,20 @@ impl From<crate::ooxml::error::OoxmlError> for Error {
 #[cfg(feature = "ooxml")]
 impl Error {
     pub(crate) fn from_opc_error(err: crate::ooxml::opc::error::OpcError) -> Self {
+        use crate::ooxml::opc::error::OpcError;
+
         match err {
-            crate::ooxml::opc::error::OpcError::IoError(e) => Error::Io(e),
-            crate::ooxml::opc::error::OpcError::ZipError(e) => Error::ZipError(e.to_string()),
-            crate::ooxml::opc::error::OpcError::XmlError(s) => Error::XmlError(s),
-            crate::ooxml::opc::error::OpcError::PartNotFound(s) => Error::ComponentNotFound(s),
+            OpcError::IoError(e) => Error::Io(e),
+            OpcError::ZipError(e) => Error::Zip(Box::new(e)),
+            OpcError::XmlError(s) => Error::xml(s),
+            OpcError::MalformedXml { part, line, source } => Error::Xml {
+                part: Some(part),
+                line: Some(line),
+                source: Box::new(source),
+            },
+            OpcError::QuickXmlError(e) => Error::xml(e),
+            OpcError::Utf8Error(e) => Error::xml(e),
+            OpcError::PartNotFound(s) => Error::MissingPart(s),
             _ => Error::Other(err.to_string()),
         }
     }
@@ -98,13 +117,13 @@ impl Error {
 #[cfg(any(feature = "ooxml", feature = "odf", feature = "formula"))]
 impl From<quick_xml::Error> for Error {
     fn from(err: quick_xml::Error) -> Self {
-        Error::XmlError(err.to_string())
+        Error::xml(err)
     }
 }
 
 #[cfg(any(feature = "ooxml", feature = "odf", feature = "iwa"))]
 impl From<soapberry_zip::Error> for Error {
     fn from(err: soapberry_zip::Error) -> Self {
-        Error::ZipError(err.to_string())
+        Error::Zip(Box::new(err))
     }
 }
@@ -8,4 +8,4 @@ pub mod conversions;
 pub mod types;
 
 // Re-exports
-pub use types::{Error, Result};
+pub use types::{BoxError, Error, Result};
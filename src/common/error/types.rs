@@ -3,10 +3,19 @@
 //! This module provides a unified error type that encompasses errors from both
 //! OLE2 and OOXML parsing, presenting a consistent API to users.
 use crate::common::binary::BinaryError;
+use std::fmt;
 use thiserror::Error;
 
+/// Boxed underlying error kept as the [`source`](std::error::Error::source) of an [`Error`].
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
 /// Main error type for Litchi operations.
+///
+/// Variants carry structured context where it is known, and the underlying
+/// quick-xml, ZIP or I/O error is available through
+/// [`source`](std::error::Error::source).
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// IO error
     #[error("IO error: {0}")]
@@ -16,6 +25,10 @@ pub enum Error {
     #[error("Parse error: {0}")]
     ParseError(String),
 
+    /// Malformed binary record
+    #[error("Binary parsing error: {0}")]
+    Binary(#[from] BinaryError),
+
     /// Invalid file format
     #[error("Invalid format: {0}")]
     InvalidFormat(String),
@@ -28,25 +41,48 @@ pub enum Error {
     #[error("Corrupted file: {0}")]
     CorruptedFile(String),
 
-    /// Stream or part not found
-    #[error("Component not found: {0}")]
-    ComponentNotFound(String),
+    /// A required package part, stream or relationship target is missing
+    #[error("Missing part: {0}")]
+    MissingPart(String),
 
-    /// XML parsing error
-    #[error("XML error: {0}")]
-    XmlError(String),
+    /// Malformed XML, with the package part and line when they are known
+    #[error("XML error{}: {source}", Location(.part.as_deref(), *.line))]
+    Xml {
+        /// Name of the package part holding the XML, such as `/word/document.xml`
+        part: Option<String>,
+        /// 1-based line of the error within the part
+        line: Option<usize>,
+        /// The underlying XML error
+        source: BoxError,
+    },
 
     /// Invalid content type
     #[error("Invalid content type: expected {expected}, got {got}")]
     InvalidContentType { expected: String, got: String },
 
-    /// ZIP archive error
+    /// The ZIP container could not be read or written
     #[error("ZIP error: {0}")]
-    ZipError(String),
+    Zip(#[source] BoxError),
 
-    /// Unsupported feature
+    /// The file uses a feature this library does not handle
     #[error("Unsupported feature: {0}")]
-    Unsupported(String),
+    UnsupportedFeature(&'static str),
+
+    /// A worksheet, cell or other reference does not resolve
+    #[error("Invalid reference: {0}")]
+    InvalidReference(String),
+
+    /// File is encrypted or password-protected
+    #[error("Encrypted file: {0}")]
+    Encrypted(String),
+
+    /// The supplied password does not unlock the file
+    #[error("Incorrect password: {0}")]
+    IncorrectPassword(String),
+
+    /// The file uses an encryption scheme that cannot be decrypted
+    #[error("Unsupported encryption: {0}")]
+    UnsupportedEncryption(String),
 
     /// Feature disabled at compile time
     #[error("Feature '{0}' is disabled. Enable it with --features {0}")]
@@ -57,11 +93,60 @@ pub enum Error {
     Other(String),
 }
 
-impl From<BinaryError> for Error {
-    fn from(err: BinaryError) -> Self {
-        Error::ParseError(format!("Binary parsing error: {}", err))
+impl Error {
+    /// Create an [`Error::Xml`] without location context.
+    pub fn xml(source: impl Into<BoxError>) -> Self {
+        Error::Xml {
+            part: None,
+            line: None,
+            source: source.into(),
+        }
+    }
+
+    /// Create an [`Error::Xml`] for a parse failure at byte `position` of `xml`.
+    #[cfg(feature = "odf")]
+    pub(crate) fn xml_at(
+        part: Option<&str>,
+        xml: &[u8],
+        position: u64,
+        source: quick_xml::Error,
+    ) -> Self {
+        Error::Xml {
+            part: part.map(str::to_string),
+            line: Some(line_at(xml, position)),
+            source: Box::new(source),
+        }
+    }
+
+    /// Create an [`Error::Zip`] from an underlying error or message.
+    pub fn zip(source: impl Into<BoxError>) -> Self {
+        Error::Zip(source.into())
+    }
+}
+
+/// Formats the optional part and line of an [`Error::Xml`].
+struct Location<'a>(Option<&'a str>, Option<usize>);
+
+impl fmt::Display for Location<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(part) = self.0 {
+            write!(f, " in {}", part)?;
+        }
+        if let Some(line) = self.1 {
+            write!(f, " at line {}", line)?;
+        }
+        Ok(())
     }
 }
 
+/// 1-based line number of byte `offset` in `xml`.
+///
+/// Offsets past the end count the lines of the whole buffer.
+#[cfg(any(feature = "ooxml", feature = "odf"))]
+pub(crate) fn line_at(xml: &[u8], offset: u64) -> usize {
+    let end = usize::try_from(offset).map_or(xml.len(), |offset| offset.min(xml.len()));
+    1 + xml[..end].iter().filter(|&&b| b == b'\n').count()
+}
+
 /// Result type for Litchi operations.
 pub type Result<T> = std::result::Result<T, Error>;
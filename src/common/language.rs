@@ -0,0 +1,230 @@
+//! Declared text languages.
+//!
+//! Word processors tag text with up to three languages at once, one per
+//! script class, because a single run may mix Latin text with East Asian
+//! or right-to-left text. [`Language`] keeps all three as BCP-47 tags.
+//!
+//! Binary formats (.doc, .rtf) store Windows language identifiers (LCIDs),
+//! which [`lcid_to_tag`] converts.
+
+/// The languages declared for a piece of text, one per script class.
+///
+/// `None` means the language is not set at that level and is inherited, or,
+/// once resolved, that the document does not declare one.
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::common::Language;
+///
+/// let mut language = Language {
+///     latin: Some("en-US".to_string()),
+///     ..Default::default()
+/// };
+/// language.apply(&Language {
+///     east_asian: Some("ja-JP".to_string()),
+///     ..Default::default()
+/// });
+/// assert_eq!(language.latin.as_deref(), Some("en-US"));
+/// assert_eq!(language.east_asian.as_deref(), Some("ja-JP"));
+/// assert_eq!(language.complex, None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Language {
+    /// Language of Latin and other non-East Asian, left-to-right text
+    /// (`w:lang/@w:val`, `fo:language`)
+    pub latin: Option<String>,
+    /// Language of East Asian text (`w:lang/@w:eastAsia`,
+    /// `style:language-asian`)
+    pub east_asian: Option<String>,
+    /// Language of complex script and right-to-left text
+    /// (`w:lang/@w:bidi`, `style:language-complex`)
+    pub complex: Option<String>,
+}
+
+impl Language {
+    /// Check whether no language is set.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.latin.is_none() && self.east_asian.is_none() && self.complex.is_none()
+    }
+
+    /// Override the languages set in `other`.
+    pub fn apply(&mut self, other: &Language) {
+        if other.latin.is_some() {
+            self.latin.clone_from(&other.latin);
+        }
+        if other.east_asian.is_some() {
+            self.east_asian.clone_from(&other.east_asian);
+        }
+        if other.complex.is_some() {
+            self.complex.clone_from(&other.complex);
+        }
+    }
+
+    /// Build a language from the LCIDs of the three script classes.
+    ///
+    /// Identifiers with no known tag are left unset.
+    pub fn from_lcids(latin: Option<u16>, east_asian: Option<u16>, complex: Option<u16>) -> Self {
+        let tag = |lcid: Option<u16>| lcid.and_then(lcid_to_tag).map(str::to_string);
+        Self {
+            latin: tag(latin),
+            east_asian: tag(east_asian),
+            complex: tag(complex),
+        }
+    }
+}
+
+/// Map a Windows language identifier (LCID) to its BCP-47 tag.
+///
+/// Returns `None` for 0 and for 0x0400, which Word uses for "no proofing",
+/// and for identifiers not in the table.
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::common::language::lcid_to_tag;
+///
+/// assert_eq!(lcid_to_tag(0x0409), Some("en-US"));
+/// assert_eq!(lcid_to_tag(0x0804), Some("zh-CN"));
+/// assert_eq!(lcid_to_tag(0x0400), None);
+/// ```
+pub fn lcid_to_tag(lcid: u16) -> Option<&'static str> {
+    Some(match lcid {
+        0x0401 => "ar-SA",
+        0x0402 => "bg-BG",
+        0x0403 => "ca-ES",
+        0x0404 => "zh-TW",
+        0x0405 => "cs-CZ",
+        0x0406 => "da-DK",
+        0x0407 => "de-DE",
+        0x0408 => "el-GR",
+        0x0409 => "en-US",
+        0x040A | 0x0C0A => "es-ES",
+        0x040B => "fi-FI",
+        0x040C => "fr-FR",
+        0x040D => "he-IL",
+        0x040E => "hu-HU",
+        0x040F => "is-IS",
+        0x0410 => "it-IT",
+        0x0411 => "ja-JP",
+        0x0412 => "ko-KR",
+        0x0413 => "nl-NL",
+        0x0414 => "nb-NO",
+        0x0415 => "pl-PL",
+        0x0416 => "pt-BR",
+        0x0417 => "rm-CH",
+        0x0418 => "ro-RO",
+        0x0419 => "ru-RU",
+        0x041A => "hr-HR",
+        0x041B => "sk-SK",
+        0x041C => "sq-AL",
+        0x041D => "sv-SE",
+        0x041E => "th-TH",
+        0x041F => "tr-TR",
+        0x0420 => "ur-PK",
+        0x0421 => "id-ID",
+        0x0422 => "uk-UA",
+        0x0423 => "be-BY",
+        0x0424 => "sl-SI",
+        0x0425 => "et-EE",
+        0x0426 => "lv-LV",
+        0x0427 => "lt-LT",
+        0x0429 => "fa-IR",
+        0x042A => "vi-VN",
+        0x042B => "hy-AM",
+        0x042C => "az-Latn-AZ",
+        0x042D => "eu-ES",
+        0x042F => "mk-MK",
+        0x0436 => "af-ZA",
+        0x0437 => "ka-GE",
+        0x0438 => "fo-FO",
+        0x0439 => "hi-IN",
+        0x043A => "mt-MT",
+        0x043E => "ms-MY",
+        0x043F => "kk-KZ",
+        0x0441 => "sw-KE",
+        0x0443 => "uz-Latn-UZ",
+        0x0445 => "bn-IN",
+        0x0446 => "pa-IN",
+        0x0447 => "gu-IN",
+        0x0449 => "ta-IN",
+        0x044A => "te-IN",
+        0x044B => "kn-IN",
+        0x044C => "ml-IN",
+        0x044E => "mr-IN",
+        0x0452 => "cy-GB",
+        0x0456 => "gl-ES",
+        0x045A => "syr-SY",
+        0x0461 => "ne-NP",
+        0x0462 => "fy-NL",
+        0x0464 => "fil-PH",
+        0x0465 => "dv-MV",
+        0x046E => "lb-LU",
+        0x0481 => "mi-NZ",
+        0x0801 => "ar-IQ",
+        0x0804 => "zh-CN",
+        0x0807 => "de-CH",
+        0x0809 => "en-GB",
+        0x080A => "es-MX",
+        0x080C => "fr-BE",
+        0x0810 => "it-CH",
+        0x0813 => "nl-BE",
+        0x0814 => "nn-NO",
+        0x0816 => "pt-PT",
+        0x081A => "sr-Latn-CS",
+        0x081D => "sv-FI",
+        0x083C => "ga-IE",
+        0x0C01 => "ar-EG",
+        0x0C04 => "zh-HK",
+        0x0C07 => "de-AT",
+        0x0C09 => "en-AU",
+        0x0C0C => "fr-CA",
+        0x0C1A => "sr-Cyrl-CS",
+        0x1004 => "zh-SG",
+        0x1009 => "en-CA",
+        0x100C => "fr-CH",
+        0x1401 => "ar-DZ",
+        0x1404 => "zh-MO",
+        0x1407 => "de-LI",
+        0x1409 => "en-NZ",
+        0x140C => "fr-LU",
+        0x1801 => "ar-MA",
+        0x1809 => "en-IE",
+        0x1C09 => "en-ZA",
+        0x2009 => "en-JM",
+        0x2409 => "en-029",
+        0x2809 => "en-BZ",
+        0x2C09 => "en-TT",
+        0x3009 => "en-ZW",
+        0x3409 => "en-PH",
+        0x4009 => "en-IN",
+        0x4409 => "en-MY",
+        0x4809 => "en-SG",
+        0x2C0A => "es-AR",
+        0x340A => "es-CL",
+        0x240A => "es-CO",
+        0x280A => "es-PE",
+        0x200A => "es-VE",
+        0x540A => "es-US",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_language_from_lcids() {
+        let language = Language::from_lcids(Some(0x0409), Some(0x0411), Some(0x0401));
+        assert_eq!(language.latin.as_deref(), Some("en-US"));
+        assert_eq!(language.east_asian.as_deref(), Some("ja-JP"));
+        assert_eq!(language.complex.as_deref(), Some("ar-SA"));
+
+        // "No proofing" and unknown identifiers are not languages
+        let language = Language::from_lcids(Some(0x0400), None, Some(0xFFFF));
+        assert!(language.is_empty());
+    }
+}
@@ -5,6 +5,59 @@
 use crate::common::Result;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Typed value of a user-defined (custom) document property.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CustomPropertyValue {
+    /// Boolean (yes/no) value
+    Bool(bool),
+    /// Integer value
+    Integer(i64),
+    /// Floating-point value
+    Float(f64),
+    /// Date and time value
+    DateTime(DateTime<Utc>),
+    /// Text value
+    String(String),
+}
+
+impl From<bool> for CustomPropertyValue {
+    fn from(value: bool) -> Self {
+        Self::Bool(value)
+    }
+}
+
+impl From<i64> for CustomPropertyValue {
+    fn from(value: i64) -> Self {
+        Self::Integer(value)
+    }
+}
+
+impl From<f64> for CustomPropertyValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
+impl From<DateTime<Utc>> for CustomPropertyValue {
+    fn from(value: DateTime<Utc>) -> Self {
+        Self::DateTime(value)
+    }
+}
+
+impl From<String> for CustomPropertyValue {
+    fn from(value: String) -> Self {
+        Self::String(value)
+    }
+}
+
+impl From<&str> for CustomPropertyValue {
+    fn from(value: &str) -> Self {
+        Self::String(value.to_string())
+    }
+}
 
 /// Unified document metadata structure.
 ///
@@ -27,6 +80,9 @@ pub struct Metadata {
     /// Document description/comments
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Document language (RFC 3066 tag, e.g. `en-US`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
     /// Template used to create the document
     #[serde(skip_serializing_if = "Option::is_none")]
     pub template: Option<String>,
@@ -75,6 +131,9 @@ pub struct Metadata {
     /// Codepage for text encoding
     #[serde(skip_serializing_if = "Option::is_none")]
     pub codepage: Option<u32>,
+    /// User-defined properties, keyed by property name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_properties: BTreeMap<String, CustomPropertyValue>,
 }
 
 impl Metadata {
@@ -87,6 +146,7 @@ impl Metadata {
             || self.author.is_some()
             || self.keywords.is_some()
             || self.description.is_some()
+            || self.language.is_some()
             || self.template.is_some()
             || self.last_modified_by.is_some()
             || self.revision.is_some()
@@ -101,6 +161,7 @@ impl Metadata {
             || self.manager.is_some()
             || self.security.is_some()
             || self.codepage.is_some()
+            || !self.custom_properties.is_empty()
     }
 
     /// Convert metadata to YAML front matter format.
@@ -130,6 +191,7 @@ impl From<crate::ole::OleMetadata> for Metadata {
             author: ole_metadata.author,
             keywords: ole_metadata.keywords,
             description: ole_metadata.comments,
+            language: None, // OLE stores a language id per text run only
             template: ole_metadata.template,
             last_modified_by: ole_metadata.last_saved_by,
             revision: ole_metadata.revision_number,
@@ -146,6 +208,7 @@ impl From<crate::ole::OleMetadata> for Metadata {
             last_printed_time: ole_metadata.last_printed_time,
             security: ole_metadata.security,
             codepage: ole_metadata.codepage,
+            custom_properties: ole_metadata.custom_properties,
         }
     }
 }
@@ -10,12 +10,19 @@ pub mod detection;
 #[cfg(any(feature = "ole", feature = "rtf"))]
 pub mod encoding;
 pub mod error;
+pub mod language;
 pub mod metadata;
+pub mod protection;
+pub mod search;
 pub mod shapes;
 pub mod simd;
+pub mod statistics;
 pub mod style;
+// Optional tracing instrumentation macros
+pub(crate) mod trace;
 /// Common unit conversion utilities (length units used across all formats)
 pub mod unit;
+pub mod walk;
 /// XML utilities
 pub mod xml;
 /// Shared byte slice for zero-copy element storage across formats
@@ -30,10 +37,12 @@ mod tests;
 pub use bom::{
     BomKind, UTF8_BOM, UTF16_BE_BOM, UTF16_LE_BOM, UTF32_BE_BOM, UTF32_LE_BOM, strip_bom, write_bom,
 };
-pub use detection::{FileFormat, detect_file_format, detect_file_format_from_bytes};
+pub use detection::{FileFormat, OpenOptions, detect_file_format, detect_file_format_from_bytes};
 pub use error::{Error, Result};
-pub use metadata::Metadata;
-pub use shapes::{PlaceholderType, ShapeType};
+pub use language::Language;
+pub use metadata::{CustomPropertyValue, Metadata};
+pub use protection::{Protection, ProtectionKind};
+pub use shapes::{PlaceholderType, Rect, ShapeType};
 pub use style::{Length, RGBColor, VerticalPosition};
 // Unit conversions
 pub use unit::{Length as MeasuredLength, LengthUnit};
@@ -0,0 +1,148 @@
+//! Editing protection and read-only recommendations.
+//!
+//! Every office format can restrict editing in a few common ways: a
+//! recommendation to open the file read-only, a "final" mark, or an enforced
+//! restriction that only allows comments, tracked changes or form filling.
+//! [`Protection`] describes the restriction of a file the same way for all
+//! of them.
+
+use super::metadata::{CustomPropertyValue, Metadata};
+
+/// The kind of editing restriction on a file.
+///
+/// Kinds are ordered from the weakest to the strongest restriction, so the
+/// restriction of a file that declares several is their maximum.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ProtectionKind {
+    /// Editing is not restricted
+    #[default]
+    None,
+    /// The file asks to be opened read-only, but may be edited
+    ReadOnlyRecommended,
+    /// The file is marked as final, discouraging further edits
+    Final,
+    /// Edits are allowed but recorded as tracked changes
+    TrackedChanges,
+    /// Only comments may be added
+    CommentsOnly,
+    /// Only form fields may be filled in
+    Forms,
+    /// No edits are allowed: the document is protected read-only, a password
+    /// is needed to save changes, or the sheets or workbook structure are
+    /// locked
+    ReadOnly,
+}
+
+/// The editing restriction of a file.
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::common::{Protection, ProtectionKind};
+///
+/// let protection = Protection::new(ProtectionKind::ReadOnlyRecommended)
+///     .or(Protection::with_password(ProtectionKind::CommentsOnly));
+/// assert_eq!(protection.kind, ProtectionKind::CommentsOnly);
+/// assert!(protection.has_password);
+/// assert!(protection.is_protected());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Protection {
+    /// The strongest restriction declared by the file
+    pub kind: ProtectionKind,
+    /// Whether a password is needed to lift a restriction
+    pub has_password: bool,
+}
+
+impl Protection {
+    /// A restriction with no password.
+    #[inline]
+    pub const fn new(kind: ProtectionKind) -> Self {
+        Self {
+            kind,
+            has_password: false,
+        }
+    }
+
+    /// A restriction lifted by a password.
+    #[inline]
+    pub const fn with_password(kind: ProtectionKind) -> Self {
+        Self {
+            kind,
+            has_password: true,
+        }
+    }
+
+    /// Check whether editing is restricted in any way.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.kind != ProtectionKind::None
+    }
+
+    /// Combine two restrictions declared by the same file, keeping the
+    /// strongest kind. The result has a password if either has one.
+    #[inline]
+    pub fn or(self, other: Protection) -> Self {
+        Self {
+            kind: self.kind.max(other.kind),
+            has_password: self.has_password || other.has_password,
+        }
+    }
+
+    /// The restriction declared by the document properties: the
+    /// `DocSecurity` flags and the `_MarkAsFinal` custom property.
+    pub fn from_metadata(metadata: &Metadata) -> Self {
+        let mut protection = Self::default();
+        if let Some(security) = metadata.security {
+            // DocSecurity: 1 = password protected, 2 = read-only
+            // recommended, 4 = read-only enforced, 8 = locked for annotations
+            if security & 4 != 0 {
+                protection.kind = ProtectionKind::ReadOnly;
+            } else if security & 8 != 0 {
+                protection.kind = ProtectionKind::CommentsOnly;
+            } else if security & 2 != 0 {
+                protection.kind = ProtectionKind::ReadOnlyRecommended;
+            }
+        }
+        if matches!(
+            metadata.custom_properties.get("_MarkAsFinal"),
+            Some(CustomPropertyValue::Bool(true))
+        ) {
+            protection = protection.or(Self::new(ProtectionKind::Final));
+        }
+        protection
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protection_from_metadata() {
+        let mut metadata = Metadata::default();
+        assert_eq!(Protection::from_metadata(&metadata), Protection::default());
+
+        metadata.security = Some(2);
+        assert_eq!(
+            Protection::from_metadata(&metadata).kind,
+            ProtectionKind::ReadOnlyRecommended
+        );
+
+        metadata
+            .custom_properties
+            .insert("_MarkAsFinal".to_string(), CustomPropertyValue::Bool(true));
+        assert_eq!(
+            Protection::from_metadata(&metadata).kind,
+            ProtectionKind::Final
+        );
+
+        metadata.security = Some(8);
+        assert_eq!(
+            Protection::from_metadata(&metadata).kind,
+            ProtectionKind::CommentsOnly
+        );
+    }
+}
@@ -0,0 +1,430 @@
+//! Text search with match positions.
+//!
+//! [`Document::find`](crate::Document::find) and
+//! [`Presentation::find`](crate::Presentation::find) search the text of a file
+//! and report where each match is: the paragraph or slide it is in, and the
+//! runs it covers. A match may span several runs, for example when part of a
+//! word is bold, so every match carries one [`MatchSegment`] per run.
+//!
+//! All offsets count characters (Unicode scalar values), not bytes.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use litchi::Document;
+//! use litchi::common::search::SearchOptions;
+//!
+//! let doc = Document::open("report.docx")?;
+//! let options = SearchOptions::new().with_case_insensitive(true);
+//! for m in doc.find("revenue", &options)? {
+//!     println!("{:?}: ...{}...", m.location(), m.snippet());
+//!     for segment in m.segments() {
+//!         println!("  run {} chars {:?}", segment.run(), segment.range());
+//!     }
+//! }
+//! # Ok::<(), litchi::Error>(())
+//! ```
+
+use super::{Error, Result};
+use std::ops::Range;
+
+/// Number of characters of context kept on each side of a match in its snippet.
+const SNIPPET_CONTEXT: usize = 30;
+
+/// Options controlling how a search pattern is matched.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Whether letters match regardless of case
+    pub case_insensitive: bool,
+    /// Whether matches must start and end at word boundaries
+    pub whole_word: bool,
+    /// Whether the pattern is a regular expression
+    ///
+    /// Requires the `regex` feature; searching fails with
+    /// [`Error::FeatureDisabled`] otherwise.
+    pub regex: bool,
+}
+
+impl SearchOptions {
+    /// Create options for a case-sensitive, literal search.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether letters match regardless of case.
+    ///
+    /// Literal patterns compare the simple lowercase mapping of each character.
+    pub fn with_case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Set whether matches must start and end at word boundaries.
+    ///
+    /// A boundary is any position not between two word characters (letters,
+    /// digits and underscores).
+    pub fn with_whole_word(mut self, whole_word: bool) -> Self {
+        self.whole_word = whole_word;
+        self
+    }
+
+    /// Set whether the pattern is a regular expression.
+    pub fn with_regex(mut self, regex: bool) -> Self {
+        self.regex = regex;
+        self
+    }
+}
+
+/// Where a match was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatchLocation {
+    /// A document paragraph, by its index in `Document::paragraphs()`
+    Paragraph {
+        /// Paragraph index
+        index: usize,
+    },
+    /// Text on a slide, by its index in `Presentation::slides()`
+    Slide {
+        /// Slide index
+        index: usize,
+        /// Index of the shape in `Slide::shapes()`, or `None` when the slide
+        /// text is searched as a whole because shapes are not available
+        shape: Option<usize>,
+    },
+}
+
+/// The part of a match that falls within one run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchSegment {
+    run: usize,
+    range: Range<usize>,
+}
+
+impl MatchSegment {
+    /// Index of the run in `Paragraph::runs()`.
+    ///
+    /// Slide text has no runs in the unified API, so it is always 0 there.
+    #[inline]
+    pub fn run(&self) -> usize {
+        self.run
+    }
+
+    /// Character range of the match within the run text.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+/// A search match with its position and surrounding text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextMatch {
+    location: MatchLocation,
+    range: Range<usize>,
+    segments: Vec<MatchSegment>,
+    text: String,
+    snippet: String,
+}
+
+impl TextMatch {
+    /// The paragraph or slide the match is in.
+    #[inline]
+    pub fn location(&self) -> MatchLocation {
+        self.location
+    }
+
+    /// Character range of the match within the paragraph or shape text.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+
+    /// The runs covered by the match, in order.
+    #[inline]
+    pub fn segments(&self) -> &[MatchSegment] {
+        &self.segments
+    }
+
+    /// The matched text.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The match with up to 30 characters of surrounding text on each side.
+    #[inline]
+    pub fn snippet(&self) -> &str {
+        &self.snippet
+    }
+}
+
+/// A compiled search pattern.
+pub(crate) struct Matcher {
+    kind: MatcherKind,
+    whole_word: bool,
+}
+
+enum MatcherKind {
+    Literal {
+        needle: Vec<char>,
+        case_insensitive: bool,
+    },
+    #[cfg(feature = "regex")]
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    /// Compile `pattern` according to `options`.
+    pub(crate) fn new(pattern: &str, options: &SearchOptions) -> Result<Self> {
+        let kind = if options.regex {
+            Self::compile_regex(pattern, options)?
+        } else {
+            MatcherKind::Literal {
+                needle: pattern
+                    .chars()
+                    .map(|c| fold(c, options.case_insensitive))
+                    .collect(),
+                case_insensitive: options.case_insensitive,
+            }
+        };
+
+        Ok(Self {
+            kind,
+            whole_word: options.whole_word,
+        })
+    }
+
+    #[cfg(feature = "regex")]
+    fn compile_regex(pattern: &str, options: &SearchOptions) -> Result<MatcherKind> {
+        regex::RegexBuilder::new(pattern)
+            .case_insensitive(options.case_insensitive)
+            .build()
+            .map(MatcherKind::Regex)
+            .map_err(|e| Error::Other(format!("Invalid search pattern: {}", e)))
+    }
+
+    #[cfg(not(feature = "regex"))]
+    fn compile_regex(_pattern: &str, _options: &SearchOptions) -> Result<MatcherKind> {
+        Err(Error::FeatureDisabled("regex".to_string()))
+    }
+
+    /// Find the non-overlapping, non-empty matches in `text` as character ranges.
+    pub(crate) fn find_all(&self, text: &str) -> Vec<Range<usize>> {
+        let chars: Vec<char> = text.chars().collect();
+        let mut matches = Vec::new();
+
+        match &self.kind {
+            MatcherKind::Literal {
+                needle,
+                case_insensitive,
+            } => {
+                if needle.is_empty() || needle.len() > chars.len() {
+                    return matches;
+                }
+                let haystack: Vec<char> =
+                    chars.iter().map(|&c| fold(c, *case_insensitive)).collect();
+                let mut start = 0;
+                while start + needle.len() <= haystack.len() {
+                    let end = start + needle.len();
+                    if haystack[start..end] == needle[..] && self.is_whole_word(&chars, start..end)
+                    {
+                        matches.push(start..end);
+                        start = end;
+                    } else {
+                        start += 1;
+                    }
+                }
+            },
+            #[cfg(feature = "regex")]
+            MatcherKind::Regex(regex) => {
+                // Character index of every byte offset that starts a character
+                let mut char_index = vec![0; text.len() + 1];
+                for (index, (offset, _)) in text.char_indices().enumerate() {
+                    char_index[offset] = index;
+                }
+                char_index[text.len()] = chars.len();
+
+                let mut offset = 0;
+                while let Some(m) = regex.find_at(text, offset) {
+                    let range = char_index[m.start()]..char_index[m.end()];
+                    if !range.is_empty() && self.is_whole_word(&chars, range.clone()) {
+                        offset = m.end();
+                        matches.push(range);
+                    } else {
+                        // Retry from the next character
+                        match text[m.start()..].chars().next() {
+                            Some(c) => offset = m.start() + c.len_utf8(),
+                            None => break,
+                        }
+                    }
+                }
+            },
+        }
+
+        matches
+    }
+
+    fn is_whole_word(&self, chars: &[char], range: Range<usize>) -> bool {
+        if !self.whole_word {
+            return true;
+        }
+        let starts_inside_word = range.start > 0
+            && is_word_char(chars[range.start - 1])
+            && is_word_char(chars[range.start]);
+        let ends_inside_word = range.end < chars.len()
+            && is_word_char(chars[range.end])
+            && is_word_char(chars[range.end - 1]);
+        !starts_inside_word && !ends_inside_word
+    }
+}
+
+/// Fold a character for comparison, keeping a one-to-one character mapping.
+#[inline]
+fn fold(c: char, case_insensitive: bool) -> char {
+    if case_insensitive {
+        c.to_lowercase().next().unwrap_or(c)
+    } else {
+        c
+    }
+}
+
+#[inline]
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Search the concatenated text of `runs` and split each match into per-run
+/// segments.
+pub(crate) fn find_in_runs<S: AsRef<str>>(
+    matcher: &Matcher,
+    runs: &[S],
+    location: MatchLocation,
+) -> Vec<TextMatch> {
+    let text: String = runs.iter().map(AsRef::as_ref).collect();
+    let matches = matcher.find_all(&text);
+    if matches.is_empty() {
+        return Vec::new();
+    }
+
+    // Character offset at which each run starts
+    let mut run_starts = Vec::with_capacity(runs.len());
+    let mut total = 0;
+    for run in runs {
+        run_starts.push(total);
+        total += run.as_ref().chars().count();
+    }
+
+    let chars: Vec<char> = text.chars().collect();
+    matches
+        .into_iter()
+        .map(|range| {
+            let segments = runs
+                .iter()
+                .enumerate()
+                .filter_map(|(run, text)| {
+                    let run_start = run_starts[run];
+                    let run_end = run_start + text.as_ref().chars().count();
+                    let start = range.start.max(run_start);
+                    let end = range.end.min(run_end);
+                    (start < end).then(|| MatchSegment {
+                        run,
+                        range: start - run_start..end - run_start,
+                    })
+                })
+                .collect();
+            let snippet_start = range.start.saturating_sub(SNIPPET_CONTEXT);
+            let snippet_end = (range.end + SNIPPET_CONTEXT).min(chars.len());
+
+            TextMatch {
+                location,
+                segments,
+                text: chars[range.clone()].iter().collect(),
+                snippet: chars[snippet_start..snippet_end].iter().collect(),
+                range,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAGRAPH: MatchLocation = MatchLocation::Paragraph { index: 0 };
+
+    fn ranges(pattern: &str, options: &SearchOptions, text: &str) -> Vec<Range<usize>> {
+        Matcher::new(pattern, options).unwrap().find_all(text)
+    }
+
+    #[test]
+    fn test_literal_options() {
+        let text = "Hello hello HELLO helloworld";
+        assert_eq!(
+            ranges("hello", &SearchOptions::new(), text),
+            [6..11, 18..23]
+        );
+
+        let options = SearchOptions::new().with_case_insensitive(true);
+        assert_eq!(
+            ranges("hello", &options, text),
+            [0..5, 6..11, 12..17, 18..23]
+        );
+
+        let options = options.with_whole_word(true);
+        assert_eq!(ranges("hello", &options, text), [0..5, 6..11, 12..17]);
+
+        // Offsets count characters, not bytes
+        assert_eq!(
+            ranges("büro", &SearchOptions::new(), "Das Büro, das büro"),
+            vec![14..18]
+        );
+        assert!(ranges("", &SearchOptions::new(), text).is_empty());
+    }
+
+    #[test]
+    fn test_match_across_runs() {
+        let matcher = Matcher::new("hello", &SearchOptions::new()).unwrap();
+        let matches = find_in_runs(&matcher, &["Say he", "l", "lo there"], PARAGRAPH);
+        assert_eq!(matches.len(), 1);
+
+        let m = &matches[0];
+        assert_eq!(m.text(), "hello");
+        assert_eq!(m.range(), 4..9);
+        assert_eq!(m.snippet(), "Say hello there");
+        let segments: Vec<_> = m.segments().iter().map(|s| (s.run(), s.range())).collect();
+        assert_eq!(segments, [(0, 4..6), (1, 0..1), (2, 0..2)]);
+    }
+
+    #[test]
+    fn test_snippet_context() {
+        let matcher = Matcher::new("needle", &SearchOptions::new()).unwrap();
+        let text = format!("{}needle{}", "a".repeat(40), "b".repeat(40));
+        let m = &find_in_runs(&matcher, &[text], PARAGRAPH)[0];
+        assert_eq!(
+            m.snippet(),
+            format!("{}needle{}", "a".repeat(30), "b".repeat(30))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "regex")]
+    fn test_regex() {
+        let options = SearchOptions::new().with_regex(true);
+        assert_eq!(ranges(r"\d+", &options, "ab 12 ü 345"), [3..5, 8..11]);
+
+        let options = options.with_whole_word(true).with_case_insensitive(true);
+        assert_eq!(ranges("cat", &options, "Cat concat CAT"), [0..3, 11..14]);
+
+        assert!(Matcher::new("(", &options).is_err());
+    }
+
+    #[test]
+    #[cfg(not(feature = "regex"))]
+    fn test_regex_disabled() {
+        let options = SearchOptions::new().with_regex(true);
+        assert!(matches!(
+            Matcher::new("a+", &options),
+            Err(Error::FeatureDisabled(_))
+        ));
+    }
+}
@@ -0,0 +1,85 @@
+//! Shape geometry shared by legacy (.ppt) and modern (.pptx) presentations.
+use crate::common::style::Length;
+
+/// Bounding box of a shape on a slide.
+///
+/// The box is the shape's unrotated frame; apply the shape's rotation and
+/// flips around its centre to get the rendered position.
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::common::{Length, Rect};
+///
+/// let rect = Rect::from_emus(914400, 0, 914400, 457200);
+/// assert_eq!(rect.x().inches(), 1.0);
+/// assert_eq!(rect.right(), Length::from_emus(1828800));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rect {
+    x: Length,
+    y: Length,
+    width: Length,
+    height: Length,
+}
+
+impl Rect {
+    /// Create a rectangle from its position and size.
+    #[inline]
+    pub const fn new(x: Length, y: Length, width: Length, height: Length) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Create a rectangle from a position and size in EMUs.
+    #[inline]
+    pub const fn from_emus(x: i64, y: i64, width: i64, height: i64) -> Self {
+        Self::new(
+            Length::from_emus(x),
+            Length::from_emus(y),
+            Length::from_emus(width),
+            Length::from_emus(height),
+        )
+    }
+
+    /// Get the distance of the left edge from the slide's left edge.
+    #[inline]
+    pub const fn x(&self) -> Length {
+        self.x
+    }
+
+    /// Get the distance of the top edge from the slide's top edge.
+    #[inline]
+    pub const fn y(&self) -> Length {
+        self.y
+    }
+
+    /// Get the width.
+    #[inline]
+    pub const fn width(&self) -> Length {
+        self.width
+    }
+
+    /// Get the height.
+    #[inline]
+    pub const fn height(&self) -> Length {
+        self.height
+    }
+
+    /// Get the position of the right edge.
+    #[inline]
+    pub const fn right(&self) -> Length {
+        Length::from_emus(self.x.emus() + self.width.emus())
+    }
+
+    /// Get the position of the bottom edge.
+    #[inline]
+    pub const fn bottom(&self) -> Length {
+        Length::from_emus(self.y.emus() + self.height.emus())
+    }
+}
@@ -4,7 +4,9 @@
 //! modern (.pptx) presentation formats.
 
 // Submodule declarations
+pub mod geometry;
 pub mod types;
 
 // Re-exports
+pub use geometry::Rect;
 pub use types::{PlaceholderType, ShapeType};
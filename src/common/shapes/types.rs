@@ -9,6 +9,7 @@ use std::fmt;
 /// This enumeration is used for both legacy .ppt and modern .pptx formats,
 /// providing a unified interface for shape type identification.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShapeType {
     /// Text box or text shape
     TextBox,
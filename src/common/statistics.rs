@@ -0,0 +1,129 @@
+//! Word and character counting shared by all formats.
+//!
+//! [`Document::statistics`](crate::Document::statistics) and
+//! [`Presentation::statistics`](crate::Presentation::statistics) count the
+//! text they extract with the functions in this module, so a document has the
+//! same counts whichever format it is saved in.
+//!
+//! Words are found with Unicode word segmentation (UAX #29): runs of
+//! punctuation and symbols such as `&` or `-` are not words, and each CJK
+//! ideograph counts as a word, as in Word's own count.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use litchi::common::statistics::count_words;
+//!
+//! assert_eq!(count_words("Fish & chips, £4"), 3);
+//! assert_eq!(count_words("state-of-the-art"), 4);
+//! assert_eq!(count_words("日本語"), 3);
+//! ```
+
+use super::walk::TextEvent;
+use std::ops::ControlFlow;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Count the words in `text` using Unicode word boundaries.
+#[inline]
+pub fn count_words(text: &str) -> usize {
+    text.unicode_words().count()
+}
+
+/// Word, character and paragraph counts of some text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct TextCounts {
+    pub words: usize,
+    pub characters: usize,
+    pub characters_no_spaces: usize,
+    pub paragraphs: usize,
+}
+
+impl TextCounts {
+    /// Add the text of one paragraph. Paragraphs with no visible text are
+    /// not counted, as in word processors.
+    pub fn add_paragraph(&mut self, text: &str) {
+        let no_spaces = text.chars().filter(|c| !c.is_whitespace()).count();
+        if no_spaces == 0 {
+            return;
+        }
+        self.words += count_words(text);
+        self.characters += text.chars().count();
+        self.characters_no_spaces += no_spaces;
+        self.paragraphs += 1;
+    }
+
+    /// Count the paragraphs reported by a `walk_text` call.
+    pub fn from_walk<E>(
+        walk: impl FnOnce(
+            &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+        ) -> Result<ControlFlow<()>, E>,
+    ) -> Result<Self, E> {
+        let mut counts = Self::default();
+        let mut paragraph = String::new();
+        // The visitor never stops the walk, so its result carries nothing
+        let _ = walk(&mut |event| {
+            match event {
+                TextEvent::Text(text) => paragraph.push_str(text),
+                // Every other event ends the current paragraph
+                _ => {
+                    counts.add_paragraph(&paragraph);
+                    paragraph.clear();
+                },
+            }
+            ControlFlow::Continue(())
+        })?;
+        counts.add_paragraph(&paragraph);
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words(""), 0);
+        assert_eq!(count_words("  \t\n"), 0);
+        assert_eq!(count_words("hello world"), 2);
+        assert_eq!(count_words("It's 3.5 km away."), 4);
+        assert_eq!(count_words("— & —"), 0);
+        assert_eq!(count_words("中文 text"), 3);
+    }
+
+    #[test]
+    fn test_text_counts_from_walk() {
+        let events = [
+            TextEvent::SlideStart(0),
+            TextEvent::ParagraphStart,
+            TextEvent::Text("Fish "),
+            TextEvent::Text("& chips"),
+            TextEvent::ParagraphStart,
+            TextEvent::Text(" "),
+            TextEvent::TableStart,
+            TextEvent::TableRowStart,
+            TextEvent::TableCellStart,
+            TextEvent::ParagraphStart,
+            TextEvent::Text("A1"),
+            TextEvent::TableEnd,
+        ];
+        let counts = TextCounts::from_walk(|visitor| {
+            for event in events {
+                if visitor(event).is_break() {
+                    break;
+                }
+            }
+            Ok::<_, ()>(ControlFlow::Continue(()))
+        })
+        .unwrap();
+        assert_eq!(
+            counts,
+            TextCounts {
+                words: 3,
+                characters: 14,
+                characters_no_spaces: 12,
+                paragraphs: 2,
+            }
+        );
+    }
+}
@@ -1,6 +1,23 @@
 use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
 
-use crate::common::unit::{EMUS_PER_CM, EMUS_PER_INCH};
+use crate::common::unit::{
+    EMUS_PER_CM, EMUS_PER_INCH, EMUS_PER_PT, EMUS_PER_TWIP, LengthUnit, split_length,
+};
+
+/// Size of a pixel in EMUs, at the CSS reference density of 96 DPI
+const EMUS_PER_PX: f64 = (EMUS_PER_INCH / 96) as f64;
+
+/// Units tried in order when formatting, from the most to the least readable
+const DISPLAY_UNITS: [LengthUnit; 5] = [
+    LengthUnit::Inch,
+    LengthUnit::Centimeter,
+    LengthUnit::Millimeter,
+    LengthUnit::Point,
+    LengthUnit::Twip,
+];
 
 /// Length measurement with units.
 ///
@@ -18,8 +35,18 @@ use crate::common::unit::{EMUS_PER_CM, EMUS_PER_INCH};
 /// // Convert to different units
 /// let inches = length.inches();
 /// let cm = length.cm();
+///
+/// // Parse and combine lengths
+/// let margin: Length = "0.5in".parse().unwrap();
+/// assert_eq!(length + margin * 2.0, Length::from_inches(2.0));
+/// assert_eq!(margin.to_string(), "36pt");
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct Length {
     /// Value in EMUs (English Metric Units)
     /// 1 inch = 914,400 EMUs
@@ -57,9 +84,7 @@ impl Length {
     /// ```
     #[inline]
     pub fn from_inches(inches: f64) -> Self {
-        Self {
-            emus: (inches * EMUS_PER_INCH as f64) as i64,
-        }
+        Self::from_unit(inches, LengthUnit::Inch)
     }
 
     /// Create a length from centimeters.
@@ -73,8 +98,39 @@ impl Length {
     /// ```
     #[inline]
     pub fn from_cm(cm: f64) -> Self {
+        Self::from_unit(cm, LengthUnit::Centimeter)
+    }
+
+    /// Create a length from points (1/72 inch).
+    #[inline]
+    pub fn from_points(points: f64) -> Self {
+        Self::from_unit(points, LengthUnit::Point)
+    }
+
+    /// Create a length from twips (1/20 point), the unit of WordprocessingML.
+    #[inline]
+    pub const fn from_twips(twips: i64) -> Self {
         Self {
-            emus: (cm * EMUS_PER_CM as f64) as i64,
+            emus: twips.saturating_mul(EMUS_PER_TWIP),
+        }
+    }
+
+    /// Create a length from a value in any unit, rounded to the nearest EMU.
+    ///
+    /// Pixels are taken at 96 DPI.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::common::{Length, LengthUnit};
+    ///
+    /// let length = Length::from_unit(12.0, LengthUnit::Point);
+    /// assert_eq!(length.emus(), 152400);
+    /// ```
+    #[inline]
+    pub fn from_unit(value: f64, unit: LengthUnit) -> Self {
+        Self {
+            emus: (value * emus_per_unit(unit)).round() as i64,
         }
     }
 
@@ -99,12 +155,164 @@ impl Length {
     /// Convert to points (1/72 inch).
     #[inline]
     pub fn points(&self) -> f64 {
-        self.inches() * 72.0
+        self.emus as f64 / EMUS_PER_PT as f64
+    }
+
+    /// Convert to twips, rounded to the nearest twip.
+    #[inline]
+    pub fn twips(&self) -> i64 {
+        crate::common::unit::emu_to_twip_i64(self.emus)
+    }
+
+    /// Convert to a value in any unit.
+    ///
+    /// Pixels are taken at 96 DPI.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::common::{Length, LengthUnit};
+    ///
+    /// let length = Length::from_inches(1.0);
+    /// assert_eq!(length.to_unit(LengthUnit::Millimeter), 25.4);
+    /// assert_eq!(length.to_unit(LengthUnit::Twip), 1440.0);
+    /// ```
+    #[inline]
+    pub fn to_unit(&self, unit: LengthUnit) -> f64 {
+        self.emus as f64 / emus_per_unit(unit)
+    }
+}
+
+/// Size of one `unit` in EMUs
+#[inline]
+fn emus_per_unit(unit: LengthUnit) -> f64 {
+    unit.emus_per_unit().unwrap_or(EMUS_PER_PX)
+}
+
+impl Add for Length {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::from_emus(self.emus + rhs.emus)
+    }
+}
+
+impl Sub for Length {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_emus(self.emus - rhs.emus)
+    }
+}
+
+impl AddAssign for Length {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        self.emus += rhs.emus;
+    }
+}
+
+impl SubAssign for Length {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        self.emus -= rhs.emus;
+    }
+}
+
+impl Neg for Length {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self::from_emus(-self.emus)
+    }
+}
+
+impl Mul<f64> for Length {
+    type Output = Self;
+
+    /// Scale the length, rounding to the nearest EMU.
+    #[inline]
+    fn mul(self, rhs: f64) -> Self {
+        Self::from_emus((self.emus as f64 * rhs).round() as i64)
+    }
+}
+
+impl Mul<Length> for f64 {
+    type Output = Length;
+
+    #[inline]
+    fn mul(self, rhs: Length) -> Length {
+        rhs * self
+    }
+}
+
+impl Div<f64> for Length {
+    type Output = Self;
+
+    /// Divide the length, rounding to the nearest EMU.
+    #[inline]
+    fn div(self, rhs: f64) -> Self {
+        Self::from_emus((self.emus as f64 / rhs).round() as i64)
+    }
+}
+
+impl Div for Length {
+    type Output = f64;
+
+    /// Get the ratio of two lengths.
+    #[inline]
+    fn div(self, rhs: Self) -> f64 {
+        self.emus as f64 / rhs.emus as f64
+    }
+}
+
+impl Sum for Length {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+impl FromStr for Length {
+    type Err = crate::Error;
+
+    /// Parse a length with a unit, such as "12pt", "1.5in", "2cm",
+    /// "914400emu" or "720twip".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::common::Length;
+    ///
+    /// let length: Length = "720twip".parse().unwrap();
+    /// assert_eq!(length, Length::from_inches(0.5));
+    /// assert!("12".parse::<Length>().is_err());
+    /// ```
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let (value, unit) = split_length(s)?;
+        if unit.is_empty() {
+            return Err(crate::Error::Other(format!(
+                "Missing length unit in '{}'",
+                s
+            )));
+        }
+        Ok(Self::from_unit(value, unit.parse()?))
     }
 }
 
 impl fmt::Display for Length {
+    /// Format the length in the first of inches, centimeters, millimeters,
+    /// points and twips that represents it exactly, or in EMUs otherwise.
+    ///
+    /// The output parses back to the same length.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{:.2}\"", self.inches())
+        let unit = DISPLAY_UNITS
+            .into_iter()
+            .find(|unit| self.emus % emus_per_unit(*unit) as i64 == 0)
+            .unwrap_or(LengthUnit::Emu);
+        let value = self.emus / emus_per_unit(unit) as i64;
+        write!(f, "{}{}", value, unit)
     }
 }
@@ -8,6 +8,7 @@
 /// Used to represent vertical alignment of text in both OLE (.doc)
 /// and OOXML (.docx) formats.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VerticalPosition {
     /// Normal position
     #[default]
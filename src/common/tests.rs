@@ -24,13 +24,23 @@ mod error_tests {
         let err = Error::CorruptedFile("corrupted".to_string());
         assert!(err.to_string().contains("corrupted"));
 
-        let err = Error::ComponentNotFound("component".to_string());
-        assert!(err.to_string().contains("component"));
+        let err = Error::MissingPart("/word/document.xml".to_string());
+        assert!(err.to_string().contains("/word/document.xml"));
 
-        let err = Error::XmlError("xml error".to_string());
-        assert!(err.to_string().contains("xml error"));
+        let err = Error::xml("xml error");
+        assert_eq!(err.to_string(), "XML error: xml error");
 
-        let err = Error::ZipError("zip error".to_string());
+        let err = Error::Xml {
+            part: Some("/xl/workbook.xml".to_string()),
+            line: Some(7),
+            source: "mismatched tag".into(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "XML error in /xl/workbook.xml at line 7: mismatched tag"
+        );
+
+        let err = Error::zip("zip error");
         assert!(err.to_string().contains("zip error"));
 
         let err = Error::InvalidContentType {
@@ -40,9 +50,12 @@ mod error_tests {
         assert!(err.to_string().contains("expected"));
         assert!(err.to_string().contains("got"));
 
-        let err = Error::Unsupported("unsupported feature".to_string());
+        let err = Error::UnsupportedFeature("unsupported feature");
         assert!(err.to_string().contains("unsupported feature"));
 
+        let err = Error::InvalidReference("Worksheet 'Missing' not found".to_string());
+        assert!(err.to_string().contains("'Missing'"));
+
         let err = Error::FeatureDisabled("test_feature".to_string());
         assert!(err.to_string().contains("test_feature"));
         assert!(err.to_string().contains("disabled"));
@@ -66,8 +79,181 @@ mod error_tests {
             available: 5,
         };
         let err: Error = bin_err.into();
-        assert!(matches!(err, Error::ParseError(_)));
+        assert!(matches!(err, Error::Binary(_)));
         assert!(err.to_string().contains("Binary"));
+        let source = std::error::Error::source(&err).expect("binary error is the source");
+        assert!(source.downcast_ref::<BinaryError>().is_some());
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_error_from_quick_xml_keeps_source() {
+        let xml_err = quick_xml::Error::Syntax(quick_xml::errors::SyntaxError::UnclosedTag);
+        let err: Error = xml_err.into();
+        assert!(matches!(
+            err,
+            Error::Xml {
+                part: None,
+                line: None,
+                ..
+            }
+        ));
+        let source = std::error::Error::source(&err).expect("quick-xml error is the source");
+        assert!(source.downcast_ref::<quick_xml::Error>().is_some());
+    }
+
+    /// Copy of a test package with the member `name` replaced, or removed when `data` is `None`.
+    #[cfg(feature = "ooxml")]
+    fn rewrite_package(path: &str, name: &str, data: Option<&[u8]>) -> Vec<u8> {
+        use soapberry_zip::office::{ArchiveReader, StreamingArchiveWriter};
+
+        let source = std::fs::read(path).unwrap();
+        let reader = ArchiveReader::new(&source).unwrap();
+        let mut writer = StreamingArchiveWriter::new();
+        for member in reader.file_names() {
+            let blob = if member == name {
+                match data {
+                    Some(data) => data.to_vec(),
+                    None => continue,
+                }
+            } else {
+                reader.read(member).unwrap()
+            };
+            writer.write_deflated(member, &blob).unwrap();
+        }
+        writer.finish_to_bytes().unwrap()
+    }
+
+    #[cfg(feature = "ooxml")]
+    const DOCX: &str = "test-data/ooxml/docx/FancyFoot.docx";
+
+    #[cfg(feature = "ooxml")]
+    fn open_error(bytes: Vec<u8>) -> Error {
+        match crate::Document::from_bytes(bytes) {
+            Ok(_) => panic!("damaged package opened"),
+            Err(err) => err,
+        }
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_truncated_zip_is_zip_error() {
+        let mut bytes = std::fs::read(DOCX).unwrap();
+        bytes.truncate(bytes.len() / 2);
+        let err = open_error(bytes);
+        assert!(matches!(err, Error::Zip(_)), "{err:?}");
+        let source = std::error::Error::source(&err).expect("zip error is the source");
+        assert!(source.downcast_ref::<soapberry_zip::Error>().is_some());
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_missing_main_part_is_missing_part() {
+        let bytes = rewrite_package(DOCX, "word/document.xml", None);
+        let err = open_error(bytes);
+        assert!(
+            matches!(&err, Error::MissingPart(part) if part == "/word/document.xml"),
+            "{err:?}"
+        );
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_zip_without_content_types_is_not_office_file() {
+        let bytes = rewrite_package(DOCX, "[Content_Types].xml", None);
+        let err = open_error(bytes);
+        assert!(matches!(err, Error::NotOfficeFile), "{err:?}");
+    }
+
+    #[cfg(feature = "ooxml")]
+    fn open_recovered(path: &str) -> crate::Document {
+        let options = crate::OpenOptions::new().with_recovery(true);
+        crate::Document::open_with_options(path, &options).unwrap()
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_recovery_opens_truncated_central_directory() {
+        const TRUNCATED: &str = "test-data/ooxml/docx/recovery_truncated.docx";
+        let err = open_error(std::fs::read(TRUNCATED).unwrap());
+        assert!(matches!(err, Error::Zip(_)), "{err:?}");
+
+        let doc = open_recovered(TRUNCATED);
+        assert!(doc.was_recovered());
+        let expected = crate::Document::open(DOCX).unwrap().text().unwrap();
+        assert_eq!(doc.text().unwrap(), expected);
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_recovery_infers_missing_content_types() {
+        const NO_CONTENT_TYPES: &str = "test-data/ooxml/docx/recovery_no_content_types.docx";
+        let err = open_error(std::fs::read(NO_CONTENT_TYPES).unwrap());
+        assert!(matches!(err, Error::NotOfficeFile), "{err:?}");
+
+        let doc = open_recovered(NO_CONTENT_TYPES);
+        assert!(doc.was_recovered());
+        let expected = crate::Document::open(DOCX).unwrap().text().unwrap();
+        assert_eq!(doc.text().unwrap(), expected);
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_recovery_leaves_intact_package_unflagged() {
+        let doc = open_recovered(DOCX);
+        assert!(!doc.was_recovered());
+        assert!(!crate::Document::open(DOCX).unwrap().was_recovered());
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_malformed_relationships_report_part_and_line() {
+        let rels = b"<Relationships>\n<Relationship Id=\"rId1\"></Broken>";
+        let bytes = rewrite_package(DOCX, "word/_rels/document.xml.rels", Some(rels));
+        let err = open_error(bytes);
+        match &err {
+            Error::Xml { part, line, .. } => {
+                assert_eq!(part.as_deref(), Some("/word/_rels/document.xml.rels"));
+                assert_eq!(*line, Some(2));
+            },
+            other => panic!("expected an XML error, got {other:?}"),
+        }
+        assert!(
+            err.to_string()
+                .contains("/word/_rels/document.xml.rels at line 2")
+        );
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_malformed_document_xml_reports_part_and_line() {
+        let xml = b"<?xml version=\"1.0\"?>\n<w:document xmlns:w=\"urn:w\">\n<w:body><w:p></w:r></w:body></w:document>";
+        let bytes = rewrite_package(DOCX, "word/document.xml", Some(xml));
+        let doc = crate::Document::from_bytes(bytes).unwrap();
+        let err = doc.paragraphs().unwrap_err();
+        match &err {
+            Error::Xml { part, line, source } => {
+                assert_eq!(part.as_deref(), Some("/word/document.xml"));
+                assert_eq!(*line, Some(3));
+                assert!(source.downcast_ref::<quick_xml::Error>().is_some());
+            },
+            other => panic!("expected an XML error, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_missing_worksheet_is_invalid_reference() {
+        let workbook =
+            crate::sheet::Workbook::open("test-data/ooxml/xlsx/InlineStrings.xlsx").unwrap();
+        let err = workbook
+            .with_workbook_trait(|wb| wb.worksheet_by_name("No such sheet").map(|_| ()))
+            .unwrap_err();
+        let err = err.downcast_ref::<Error>().expect("a litchi error");
+        assert!(
+            matches!(err, Error::InvalidReference(msg) if msg.contains("No such sheet")),
+            "{err:?}"
+        );
     }
 }
 
@@ -107,8 +293,48 @@ mod length_tests {
 
     #[test]
     fn test_length_display() {
-        let length = Length::from_inches(1.5);
-        assert_eq!(format!("{}", length), "1.50\"");
+        assert_eq!(Length::from_inches(2.0).to_string(), "2in");
+        assert_eq!(Length::from_inches(1.5).to_string(), "108pt");
+        assert_eq!(Length::from_cm(3.0).to_string(), "3cm");
+        assert_eq!(Length::from_twips(15).to_string(), "15twip");
+        assert_eq!(Length::from_emus(-7).to_string(), "-7emu");
+    }
+
+    #[test]
+    fn test_length_parse() {
+        let cases = [
+            ("12pt", 152_400),
+            ("1.5in", 1_371_600),
+            ("2cm", 720_000),
+            ("914400emu", 914_400),
+            ("720twip", 457_200),
+            (" -3mm ", -108_000),
+            ("1px", 9_525),
+        ];
+        for (text, emus) in cases {
+            assert_eq!(text.parse::<Length>().unwrap().emus(), emus, "{text}");
+        }
+        for text in ["", "12", "pt", "12parsecs"] {
+            assert!(text.parse::<Length>().is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn test_length_arithmetic() {
+        let inch = Length::from_inches(1.0);
+        let pt = Length::from_points(1.0);
+        assert_eq!(inch + pt, Length::from_emus(927_100));
+        assert_eq!(inch - pt * 72.0, Length::default());
+        assert_eq!(-inch / 2.0, Length::from_inches(-0.5));
+        assert_eq!(2.0 * inch, Length::from_inches(2.0));
+        assert_eq!(inch / pt, 72.0);
+        assert_eq!([inch, pt, pt].into_iter().sum::<Length>(), inch + pt * 2.0);
+
+        let mut length = inch;
+        length += pt;
+        length -= inch;
+        assert_eq!(length, pt);
+        assert!(pt < inch);
     }
 
     #[test]
@@ -130,6 +356,42 @@ mod length_tests {
     }
 }
 
+#[cfg(test)]
+mod length_property_tests {
+    use crate::common::{Length, LengthUnit};
+    use proptest::prelude::*;
+
+    fn unit_strategy() -> impl Strategy<Value = LengthUnit> {
+        prop::sample::select(vec![
+            LengthUnit::Millimeter,
+            LengthUnit::Centimeter,
+            LengthUnit::Point,
+            LengthUnit::Pica,
+            LengthUnit::Inch,
+            LengthUnit::Pixel,
+            LengthUnit::Emu,
+            LengthUnit::Twip,
+        ])
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(500))]
+
+        #[test]
+        fn prop_unit_round_trip(emus in -100_000_000i64..100_000_000, unit in unit_strategy()) {
+            let length = Length::from_emus(emus);
+            let back = Length::from_unit(length.to_unit(unit), unit);
+            prop_assert!((back.emus() - emus).abs() <= 1, "{} via {}", emus, unit);
+        }
+
+        #[test]
+        fn prop_display_round_trip(emus in -100_000_000i64..100_000_000) {
+            let length = Length::from_emus(emus);
+            prop_assert_eq!(length.to_string().parse::<Length>().unwrap(), length);
+        }
+    }
+}
+
 #[cfg(test)]
 mod vertical_position_tests {
     use crate::common::VerticalPosition;
@@ -559,3 +821,98 @@ mod detection_utils_tests {
         assert!(!find_in_buffer(&buffer, ZIP_SIGNATURE));
     }
 }
+
+#[cfg(all(test, feature = "serde", feature = "ooxml"))]
+mod serde_tests {
+    use crate::Document;
+    use crate::sheet::{CellValue, open_workbook};
+    use serde_json::{Value, json};
+    use std::path::{Path, PathBuf};
+
+    fn test_data_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data")
+    }
+
+    /// Compare against `test-data/snapshots/<name>`, which is rewritten
+    /// instead when `LITCHI_UPDATE_SNAPSHOTS` is set.
+    fn assert_snapshot(name: &str, actual: Value) {
+        let path = test_data_path().join("snapshots").join(name);
+        if std::env::var_os("LITCHI_UPDATE_SNAPSHOTS").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            let pretty = serde_json::to_string_pretty(&actual).unwrap();
+            std::fs::write(&path, pretty + "\n").unwrap();
+            return;
+        }
+        let expected = std::fs::read_to_string(&path).unwrap_or_else(|_| {
+            panic!(
+                "missing snapshot {}; run with LITCHI_UPDATE_SNAPSHOTS=1",
+                name
+            )
+        });
+        let expected: Value = serde_json::from_str(&expected).unwrap();
+        assert_eq!(
+            actual, expected,
+            "{} changed; run with LITCHI_UPDATE_SNAPSHOTS=1 to accept",
+            name
+        );
+    }
+
+    #[test]
+    fn test_docx_json_snapshot() {
+        let doc = Document::open(test_data_path().join("ooxml/docx/FancyFoot.docx")).unwrap();
+        let actual = json!({
+            "elements": doc.elements().unwrap(),
+        });
+        assert_snapshot("FancyFoot.docx.json", actual);
+    }
+
+    #[test]
+    fn test_xlsx_json_snapshot() {
+        let path: &Path = &test_data_path().join("ooxml/xlsx/InlineStrings.xlsx");
+        let workbook = open_workbook(path).unwrap();
+        let mut sheets = Vec::new();
+        let mut worksheets = workbook.worksheets();
+        while let Some(sheet) = worksheets.next() {
+            let sheet = sheet.unwrap();
+            let mut rows = Vec::new();
+            let mut iter = sheet.rows();
+            while let Some(row) = iter.next() {
+                rows.push(row.unwrap().into_owned());
+            }
+            sheets.push(json!({ "name": sheet.name(), "rows": rows }));
+        }
+        assert_snapshot("InlineStrings.xlsx.json", json!({ "worksheets": sheets }));
+    }
+
+    #[test]
+    fn test_cell_value_round_trip() {
+        let value = CellValue::Formula {
+            formula: "SUM(A1:A3)".to_string(),
+            cached_value: Some(Box::new(CellValue::Float(6.0))),
+            is_array: false,
+            array_range: None,
+        };
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(serde_json::from_str::<CellValue>(&json).unwrap(), value);
+    }
+
+    #[cfg(feature = "formula")]
+    #[test]
+    fn test_math_node_round_trip() {
+        use crate::formula::{MathNode, Operator};
+
+        let node = MathNode::Frac {
+            numerator: vec![
+                MathNode::Number("1".into()),
+                MathNode::Operator(Operator::Plus),
+                MathNode::Text("x".into()),
+            ],
+            denominator: vec![MathNode::Number("2".into())],
+            line_thickness: None,
+            frac_type: None,
+        };
+        let json = serde_json::to_string(&node).unwrap();
+        let owned: MathNode<'static> = serde_json::from_str(&json).unwrap();
+        assert_eq!(owned, node);
+    }
+}
@@ -0,0 +1,155 @@
+//! Optional `tracing` instrumentation.
+//!
+//! The macros here forward to the [`tracing`](https://docs.rs/tracing) macros
+//! of the same name when the `tracing` feature is enabled. Without the feature
+//! spans are a unit placeholder and events expand to nothing, so field
+//! expressions are not evaluated and the instrumentation costs nothing.
+//!
+//! Span and event targets are the module paths of the call sites, so a filter
+//! such as `litchi=debug` selects everything the library emits. Fields carry
+//! part names and byte sizes so that slow parts can be aggregated.
+
+// Not every feature combination uses every macro
+#![allow(unused_imports, unused_macros)]
+
+/// Placeholder for an entered span when the `tracing` feature is disabled.
+#[cfg(not(feature = "tracing"))]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Span;
+
+#[cfg(not(feature = "tracing"))]
+impl Span {
+    /// Does nothing; mirrors `tracing::Span::entered`.
+    #[inline(always)]
+    pub(crate) fn entered(self) -> Self {
+        self
+    }
+}
+
+/// Create an info-level span, or a no-op [`Span`] without the `tracing` feature.
+#[cfg(feature = "tracing")]
+macro_rules! info_span {
+    ($($args:tt)*) => {
+        ::tracing::info_span!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! info_span {
+    ($($args:tt)*) => {
+        $crate::common::trace::Span
+    };
+}
+
+/// Create a debug-level span, or a no-op [`Span`] without the `tracing` feature.
+#[cfg(feature = "tracing")]
+macro_rules! debug_span {
+    ($($args:tt)*) => {
+        ::tracing::debug_span!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! debug_span {
+    ($($args:tt)*) => {
+        $crate::common::trace::Span
+    };
+}
+
+/// Emit a warn-level event for a recoverable anomaly.
+#[cfg(feature = "tracing")]
+macro_rules! warn_event {
+    ($($args:tt)*) => {
+        ::tracing::warn!($($args)*)
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+macro_rules! warn_event {
+    ($($args:tt)*) => {};
+}
+
+pub(crate) use {debug_span, info_span, warn_event as warn};
+
+#[cfg(all(test, feature = "tracing"))]
+mod tests {
+    use std::sync::{Arc, Mutex};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Subscriber that records the names of the spans it sees.
+    #[derive(Clone, Default)]
+    struct SpanNames(Arc<Mutex<Vec<&'static str>>>);
+
+    impl SpanNames {
+        fn contains(&self, name: &str) -> bool {
+            self.0.lock().unwrap().contains(&name)
+        }
+    }
+
+    impl Subscriber for SpanNames {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            let mut names = self.0.lock().unwrap();
+            names.push(span.metadata().name());
+            Id::from_u64(names.len() as u64)
+        }
+
+        fn record(&self, _: &Id, _: &Record<'_>) {}
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, _: &Event<'_>) {}
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_pptx_spans() {
+        let names = SpanNames::default();
+        tracing::subscriber::with_default(names.clone(), || {
+            let pres =
+                crate::Presentation::open("test-data/ooxml/pptx/scatter-chart.pptx").unwrap();
+            pres.text().unwrap();
+        });
+
+        assert!(names.contains("open_package"));
+        assert!(names.contains("read_parts"));
+        assert!(names.contains("parse_slide"));
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_xlsx_spans() {
+        let names = SpanNames::default();
+        tracing::subscriber::with_default(names.clone(), || {
+            let workbook =
+                crate::sheet::Workbook::open("test-data/ooxml/xlsx/InlineStrings.xlsx").unwrap();
+            workbook
+                .with_workbook_trait(|wb| wb.worksheet_by_index(0).map(|_| ()))
+                .unwrap();
+        });
+
+        assert!(names.contains("open_package"));
+        assert!(names.contains("parse_worksheet"));
+    }
+
+    #[cfg(feature = "ole")]
+    #[test]
+    fn test_xls_spans() {
+        let names = SpanNames::default();
+        tracing::subscriber::with_default(names.clone(), || {
+            crate::sheet::Workbook::open("test-data/ole/xls/ComplexFunctionTestCaseData.xls")
+                .unwrap();
+        });
+
+        assert!(names.contains("open_package"));
+        assert!(names.contains("parse_worksheet"));
+    }
+}
@@ -5,6 +5,7 @@
 //!
 
 use crate::Result;
+use fast_float2::parse_partial;
 use std::cmp::Ordering;
 use std::fmt;
 use std::str::FromStr;
@@ -104,6 +105,10 @@ pub enum LengthUnit {
     Mile,
     /// Pixel
     Pixel,
+    /// English Metric Unit (1/914,400 inch)
+    Emu,
+    /// Twip (1/20 point)
+    Twip,
 }
 
 impl LengthUnit {
@@ -121,9 +126,33 @@ impl LengthUnit {
             Self::Foot => "ft",
             Self::Mile => "mi",
             Self::Pixel => "px",
+            Self::Emu => "emu",
+            Self::Twip => "twip",
         }
     }
 
+    /// Get the size of one unit in EMUs
+    ///
+    /// Returns `None` for pixels, whose size depends on the DPI.
+    #[inline]
+    pub fn emus_per_unit(&self) -> Option<f64> {
+        let emus = match self {
+            Self::Millimeter => EMUS_PER_MM,
+            Self::Centimeter => EMUS_PER_CM,
+            Self::Meter => EMUS_PER_CM * 100,
+            Self::Kilometer => EMUS_PER_CM * 100_000,
+            Self::Point => EMUS_PER_PT,
+            Self::Pica => EMUS_PER_PT * 12,
+            Self::Inch => EMUS_PER_INCH,
+            Self::Foot => EMUS_PER_INCH * 12,
+            Self::Mile => EMUS_PER_INCH * 63_360,
+            Self::Pixel => return None,
+            Self::Emu => 1,
+            Self::Twip => EMUS_PER_TWIP,
+        };
+        Some(emus as f64)
+    }
+
     /// Parse unit from string
     fn from_str_internal(s: &str) -> Option<Self> {
         match s {
@@ -137,6 +166,8 @@ impl LengthUnit {
             "ft" => Some(Self::Foot),
             "mi" => Some(Self::Mile),
             "px" => Some(Self::Pixel),
+            "emu" => Some(Self::Emu),
+            "twip" | "twips" => Some(Self::Twip),
             _ => None,
         }
     }
@@ -236,17 +267,9 @@ impl Length {
     pub fn to_pixels(&self, dpi: u32) -> Result<Self> {
         let dpi_f64 = dpi as f64;
 
-        let pixel_value = match self.unit {
-            LengthUnit::Pixel => self.value,
-            LengthUnit::Inch => self.value * dpi_f64,
-            LengthUnit::Centimeter => self.value / 2.54 * dpi_f64,
-            LengthUnit::Millimeter => self.value / 25.4 * dpi_f64,
-            LengthUnit::Meter => self.value / 0.0254 * dpi_f64,
-            LengthUnit::Kilometer => self.value / 0.0000254 * dpi_f64,
-            LengthUnit::Point => self.value / 72.0 * dpi_f64,
-            LengthUnit::Pica => self.value / 6.0 * dpi_f64,
-            LengthUnit::Foot => self.value * 12.0 * dpi_f64,
-            LengthUnit::Mile => self.value * 63360.0 * dpi_f64,
+        let pixel_value = match self.unit.emus_per_unit() {
+            Some(emus) => self.value * emus / EMUS_PER_INCH as f64 * dpi_f64,
+            None => self.value,
         };
 
         Ok(Self::new(pixel_value, LengthUnit::Pixel))
@@ -264,22 +287,7 @@ impl Length {
     /// assert!((inches.value() - 1.0).abs() < 0.001);
     /// ```
     pub fn to_inches(&self) -> Result<Self> {
-        let inch_value = match self.unit {
-            LengthUnit::Inch => self.value,
-            LengthUnit::Centimeter => self.value / 2.54,
-            LengthUnit::Millimeter => self.value / 25.4,
-            LengthUnit::Meter => self.value / 0.0254,
-            LengthUnit::Kilometer => self.value / 0.0000254,
-            LengthUnit::Point => self.value / 72.0,
-            LengthUnit::Pica => self.value / 6.0,
-            LengthUnit::Foot => self.value * 12.0,
-            LengthUnit::Mile => self.value * 63360.0,
-            LengthUnit::Pixel => {
-                return Err(crate::Error::Other(
-                    "Cannot convert pixels to inches without DPI information".to_string(),
-                ));
-            },
-        };
+        let inch_value = self.value * self.unit_emus("inches")? / EMUS_PER_INCH as f64;
 
         Ok(Self::new(inch_value, LengthUnit::Inch))
     }
@@ -296,25 +304,29 @@ impl Length {
     /// assert!((cm.value() - 2.54).abs() < 0.001);
     /// ```
     pub fn to_centimeters(&self) -> Result<Self> {
-        let cm_value = match self.unit {
-            LengthUnit::Centimeter => self.value,
-            LengthUnit::Inch => self.value * 2.54,
-            LengthUnit::Millimeter => self.value / 10.0,
-            LengthUnit::Meter => self.value * 100.0,
-            LengthUnit::Kilometer => self.value * 100000.0,
-            LengthUnit::Point => self.value / 72.0 * 2.54,
-            LengthUnit::Pica => self.value / 6.0 * 2.54,
-            LengthUnit::Foot => self.value * 12.0 * 2.54,
-            LengthUnit::Mile => self.value * 63360.0 * 2.54,
-            LengthUnit::Pixel => {
-                return Err(crate::Error::Other(
-                    "Cannot convert pixels to centimeters without DPI information".to_string(),
-                ));
-            },
-        };
+        let cm_value = self.value * self.unit_emus("centimeters")? / EMUS_PER_CM as f64;
 
         Ok(Self::new(cm_value, LengthUnit::Centimeter))
     }
+
+    /// Convert to an EMU-based [`Length`](crate::common::Length)
+    ///
+    /// Returns `None` for pixels, which need a DPI to convert.
+    pub fn to_length(&self) -> Option<crate::common::Length> {
+        self.unit
+            .emus_per_unit()
+            .map(|_| crate::common::Length::from_unit(self.value, self.unit))
+    }
+
+    /// Size of the unit in EMUs, or an error naming the `target` unit for pixels
+    fn unit_emus(&self, target: &str) -> Result<f64> {
+        self.unit.emus_per_unit().ok_or_else(|| {
+            crate::Error::Other(format!(
+                "Cannot convert pixels to {} without DPI information",
+                target
+            ))
+        })
+    }
 }
 
 impl FromStr for Length {
@@ -336,36 +348,12 @@ impl FromStr for Length {
     /// assert_eq!(length.unit(), LengthUnit::Point);
     /// ```
     fn from_str(s: &str) -> Result<Self> {
-        let mut digits = String::new();
-        let mut non_digits = String::new();
-        let mut seen_dot = false;
-
-        for c in s.chars() {
-            if c.is_ascii_digit() || (c == '.' && !seen_dot) || (c == '-' && digits.is_empty()) {
-                if c == '.' {
-                    seen_dot = true;
-                }
-                digits.push(c);
-            } else {
-                non_digits.push(c);
-            }
-        }
-
-        if digits.is_empty() {
-            return Err(crate::Error::Other(format!(
-                "No numeric value found in '{}'",
-                s
-            )));
-        }
+        let (value, unit) = split_length(s)?;
 
-        let value: f64 = digits.parse().map_err(|_| {
-            crate::Error::Other(format!("Failed to parse numeric value from '{}'", s))
-        })?;
-
-        let unit = if non_digits.is_empty() {
+        let unit = if unit.is_empty() {
             LengthUnit::Centimeter // Default to cm
         } else {
-            LengthUnit::from_str(&non_digits)?
+            LengthUnit::from_str(unit)?
         };
 
         Ok(Self::new(value, unit))
@@ -378,6 +366,20 @@ impl fmt::Display for Length {
     }
 }
 
+/// Split a length string such as "2.5cm" into its value and unit
+///
+/// Surrounding whitespace is ignored and the unit may be empty.
+pub(crate) fn split_length(s: &str) -> Result<(f64, &str)> {
+    let trimmed = s.trim();
+    match parse_partial::<f64, _>(trimmed) {
+        Ok((value, consumed)) => Ok((value, trimmed[consumed..].trim())),
+        Err(_) => Err(crate::Error::Other(format!(
+            "No numeric value found in '{}'",
+            s
+        ))),
+    }
+}
+
 impl PartialEq for Length {
     fn eq(&self, other: &Self) -> bool {
         if self.unit != other.unit {
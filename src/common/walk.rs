@@ -0,0 +1,124 @@
+//! Streaming text extraction with early exit.
+//!
+//! [`Document::walk_text`](crate::Document::walk_text) and
+//! [`Presentation::walk_text`](crate::Presentation::walk_text) report the
+//! text of a file as a sequence of [`TextEvent`]s to a visitor, in reading
+//! order, as each paragraph, table or slide is parsed. The visitor returns
+//! [`ControlFlow::Break`] to stop the walk; nothing after that point is
+//! parsed. This suits scanners that look for a pattern in very large files
+//! and want to stop at the first hit.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! use litchi::Presentation;
+//! use litchi::common::walk::TextEvent;
+//! use std::ops::ControlFlow;
+//!
+//! let pres = Presentation::open("deck.pptx")?;
+//! let mut slide = 0;
+//! let found = pres.walk_text(|event| match event {
+//!     TextEvent::SlideStart(index) => {
+//!         slide = index;
+//!         ControlFlow::Continue(())
+//!     },
+//!     TextEvent::Text(text) if text.contains("CONFIDENTIAL") => ControlFlow::Break(()),
+//!     _ => ControlFlow::Continue(()),
+//! })?;
+//! if found.is_break() {
+//!     println!("marked confidential on slide {}", slide + 1);
+//! }
+//! # Ok::<(), litchi::Error>(())
+//! ```
+
+use std::ops::ControlFlow;
+
+/// A piece of structure or text reported while walking a file.
+///
+/// Events arrive in reading order. Every `Text` belongs to the paragraph
+/// started by the closest `ParagraphStart` before it; a paragraph may report
+/// its text in several chunks, for example one per run. Tabs and line breaks
+/// within a paragraph are reported as `"\t"` and `"\n"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TextEvent<'a> {
+    /// A slide begins, with its zero-based index
+    SlideStart(usize),
+    /// A paragraph begins
+    ParagraphStart,
+    /// Text of the current paragraph
+    Text(&'a str),
+    /// A table begins
+    TableStart,
+    /// A row of the current table begins
+    TableRowStart,
+    /// A cell of the current row begins; its paragraphs follow
+    TableCellStart,
+    /// The current table ends
+    TableEnd,
+}
+
+/// Report `$event` to `$visitor`, returning `Ok(ControlFlow::Break(()))`
+/// from the enclosing function when the visitor asks to stop.
+macro_rules! visit {
+    ($visitor:expr, $event:expr) => {
+        if $visitor($event).is_break() {
+            return Ok(::std::ops::ControlFlow::Break(()));
+        }
+    };
+}
+pub(crate) use visit;
+
+/// Report each line of `text` as a paragraph.
+///
+/// Used by formats that only expose the text of a slide or cell, with one
+/// paragraph per line.
+pub(crate) fn visit_lines(
+    text: &str,
+    visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+) -> ControlFlow<()> {
+    for line in text.lines() {
+        visitor(TextEvent::ParagraphStart)?;
+        if !line.is_empty() {
+            visitor(TextEvent::Text(line))?;
+        }
+    }
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visit_lines() {
+        let mut events = Vec::new();
+        let flow = visit_lines("Title\n\nBody", &mut |event| {
+            events.push(format!("{event:?}"));
+            ControlFlow::Continue(())
+        });
+        assert!(flow.is_continue());
+        assert_eq!(
+            events,
+            [
+                "ParagraphStart",
+                "Text(\"Title\")",
+                "ParagraphStart",
+                "ParagraphStart",
+                "Text(\"Body\")"
+            ]
+        );
+
+        let mut count = 0;
+        let flow = visit_lines("a\nb\nc", &mut |_| {
+            count += 1;
+            if count == 2 {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        });
+        assert!(flow.is_break());
+        assert_eq!(count, 2);
+    }
+}
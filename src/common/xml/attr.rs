@@ -0,0 +1,35 @@
+//! Attribute lookup on quick-xml start tags.
+
+use quick_xml::events::BytesStart;
+use quick_xml::events::attributes::Attribute;
+
+/// Get the value of the attribute with local name `name`, ignoring its
+/// namespace prefix.
+///
+/// Entities are unescaped; a value with a malformed entity is returned as is.
+#[cfg(feature = "ooxml")]
+pub(crate) fn attr(e: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == name)
+        .map(value)
+}
+
+/// Get the value of the attribute with qualified name `name`, such as
+/// `style:name`.
+///
+/// Entities are unescaped; a value with a malformed entity is returned as is.
+#[cfg(feature = "odf")]
+pub(crate) fn qualified_attr(e: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == name)
+        .map(value)
+}
+
+fn value(attr: Attribute<'_>) -> String {
+    match attr.unescape_value() {
+        Ok(value) => value.into_owned(),
+        Err(_) => String::from_utf8_lossy(&attr.value).into_owned(),
+    }
+}
@@ -1,3 +1,9 @@
+#[cfg(any(feature = "ooxml", feature = "odf"))]
+mod attr;
 pub mod escape;
 
+#[cfg(feature = "ooxml")]
+pub(crate) use attr::attr;
+#[cfg(feature = "odf")]
+pub(crate) use attr::qualified_attr;
 pub use escape::*;
@@ -0,0 +1,877 @@
+//! Comparison of two Word documents.
+//!
+//! [`diff`] aligns the paragraphs of two documents with a shortest edit script
+//! over hashes of their (normalized) text, then compares each pair of changed
+//! paragraphs word by word. Tables are compared cell by cell, matching tables
+//! and cells by position.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use litchi::Document;
+//! use litchi::document::{DiffOptions, diff};
+//!
+//! let old = Document::open("contract_v1.docx")?;
+//! let new = Document::open("contract_v2.docx")?;
+//!
+//! let changes = diff(&old, &new, &DiffOptions::new().with_ignore_whitespace(true))?;
+//! println!("{}", changes.to_markdown());
+//! # Ok::<(), litchi::common::Error>(())
+//! ```
+
+use super::{Document, DocumentElement, Paragraph};
+use crate::common::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt::Write as FmtWrite;
+use std::hash::{Hash, Hasher};
+
+/// Edit distance beyond which two sequences are treated as entirely replaced.
+///
+/// Bounds the memory of the alignment, which grows with the square of the
+/// number of edits.
+const MAX_EDIT_DISTANCE: usize = 4096;
+
+/// Minimum share of matching words for a deleted and an inserted paragraph to
+/// be reported as one modified paragraph.
+const MODIFIED_SIMILARITY: f64 = 0.5;
+
+/// Options controlling how two documents are compared.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DiffOptions {
+    /// Whether letters compare regardless of case
+    pub ignore_case: bool,
+    /// Whether changes that only add, remove or resize whitespace are ignored
+    pub ignore_whitespace: bool,
+    /// Whether paragraphs with the same text but different bold, italic or
+    /// strikethrough runs are reported as [`ChangeKind::Formatting`]
+    pub include_formatting: bool,
+}
+
+impl DiffOptions {
+    /// Create options for an exact, text-only comparison.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether letters compare regardless of case.
+    pub fn with_ignore_case(mut self, ignore_case: bool) -> Self {
+        self.ignore_case = ignore_case;
+        self
+    }
+
+    /// Set whether whitespace-only changes are ignored.
+    pub fn with_ignore_whitespace(mut self, ignore_whitespace: bool) -> Self {
+        self.ignore_whitespace = ignore_whitespace;
+        self
+    }
+
+    /// Set whether formatting-only changes are reported.
+    pub fn with_formatting(mut self, include_formatting: bool) -> Self {
+        self.include_formatting = include_formatting;
+        self
+    }
+}
+
+/// Kind of a reported change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    /// Present only in the new document
+    Inserted,
+    /// Present only in the old document
+    Deleted,
+    /// Present in both documents with different text
+    Modified,
+    /// Same text in both documents, different character formatting
+    Formatting,
+}
+
+/// A piece of text in a word-level diff.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffSegment {
+    /// Text present in both versions (as written in the new one)
+    Equal(String),
+    /// Text only in the old version
+    Deleted(String),
+    /// Text only in the new version
+    Inserted(String),
+}
+
+/// A changed paragraph.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ParagraphChange {
+    /// What happened to the paragraph
+    pub kind: ChangeKind,
+    /// Index among the old document's body paragraphs, if it exists there
+    pub old_index: Option<usize>,
+    /// Index among the new document's body paragraphs, if it exists there
+    pub new_index: Option<usize>,
+    /// Word-level diff of the paragraph text
+    pub segments: Vec<DiffSegment>,
+}
+
+impl ParagraphChange {
+    /// Text of the paragraph in the old document.
+    pub fn old_text(&self) -> String {
+        side_text(&self.segments, false)
+    }
+
+    /// Text of the paragraph in the new document.
+    pub fn new_text(&self) -> String {
+        side_text(&self.segments, true)
+    }
+}
+
+/// A changed table cell, located by position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellChange {
+    /// Index of the table among the document's tables
+    pub table: usize,
+    /// Row index within the table
+    pub row: usize,
+    /// Cell index within the row
+    pub column: usize,
+    /// What happened to the cell
+    pub kind: ChangeKind,
+    /// Word-level diff of the cell text
+    pub segments: Vec<DiffSegment>,
+}
+
+impl CellChange {
+    /// Text of the cell in the old document.
+    pub fn old_text(&self) -> String {
+        side_text(&self.segments, false)
+    }
+
+    /// Text of the cell in the new document.
+    pub fn new_text(&self) -> String {
+        side_text(&self.segments, true)
+    }
+}
+
+/// Differences between two documents, as returned by [`diff`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocumentDiff {
+    /// Changed paragraphs, in document order
+    pub paragraphs: Vec<ParagraphChange>,
+    /// Changed table cells, in table, row and column order
+    pub cells: Vec<CellChange>,
+}
+
+impl DocumentDiff {
+    /// Whether the documents compared equal.
+    pub fn is_empty(&self) -> bool {
+        self.paragraphs.is_empty() && self.cells.is_empty()
+    }
+
+    /// Total number of changes.
+    pub fn len(&self) -> usize {
+        self.paragraphs.len() + self.cells.len()
+    }
+
+    /// Render the changes as a Markdown list.
+    ///
+    /// Deleted text is shown as `~~deleted~~` and inserted text as
+    /// `**inserted**`. Paragraph, table, row and column numbers are 1-based;
+    /// paragraph numbers refer to the new document except for deletions.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        for change in &self.paragraphs {
+            let (number, label) = match change.kind {
+                ChangeKind::Inserted => (change.new_index, " (inserted)"),
+                ChangeKind::Deleted => (change.old_index, " (deleted)"),
+                ChangeKind::Modified => (change.new_index, ""),
+                ChangeKind::Formatting => (change.new_index, " (formatting)"),
+            };
+            let _ = write!(out, "- Paragraph {}{}: ", number.unwrap_or(0) + 1, label);
+            render_segments(&mut out, &change.segments);
+            out.push('\n');
+        }
+        for change in &self.cells {
+            let _ = write!(
+                out,
+                "- Table {}, row {}, column {}: ",
+                change.table + 1,
+                change.row + 1,
+                change.column + 1
+            );
+            render_segments(&mut out, &change.segments);
+            out.push('\n');
+        }
+        out
+    }
+}
+
+/// Compare two documents.
+///
+/// Body paragraphs (those outside tables, as listed by
+/// [`Document::elements`]) are aligned on their text; a deleted and an inserted paragraph
+/// at the same place are reported as one [`ChangeKind::Modified`] paragraph
+/// when at least half of their words match. Tables are compared by position.
+///
+/// # Errors
+///
+/// Returns an error if the paragraphs, runs or tables of either document
+/// cannot be read.
+pub fn diff(old: &Document, new: &Document, options: &DiffOptions) -> Result<DocumentDiff> {
+    let (old_blocks, old_tables) = snapshot(old, options)?;
+    let (new_blocks, new_tables) = snapshot(new, options)?;
+    let paragraphs = diff_blocks(&old_blocks, &new_blocks, options);
+    let cells = diff_tables(&old_tables, &new_tables, options);
+
+    Ok(DocumentDiff { paragraphs, cells })
+}
+
+/// Character formatting of a stretch of text, for formatting-only changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FormatSpan {
+    chars: usize,
+    bold: bool,
+    italic: bool,
+    strikethrough: bool,
+}
+
+/// Paragraph text and formatting, read once from a document.
+#[derive(Debug, Default)]
+struct Block {
+    text: String,
+    formatting: Vec<FormatSpan>,
+}
+
+/// Table cell texts by row.
+type TableGrid = Vec<Vec<String>>;
+
+/// Read the body paragraphs and the tables of a document.
+///
+/// Paragraphs inside tables are left to the table comparison.
+fn snapshot(doc: &Document, options: &DiffOptions) -> Result<(Vec<Block>, Vec<TableGrid>)> {
+    let mut blocks = Vec::new();
+    let mut tables = Vec::new();
+    for element in doc.elements()? {
+        match element {
+            DocumentElement::Paragraph(para) => blocks.push(snapshot_paragraph(&para, options)?),
+            DocumentElement::Table(table) => {
+                let mut grid = Vec::new();
+                for row in table.rows()? {
+                    let cells = row.cells()?;
+                    grid.push(cells.iter().map(|c| c.text()).collect::<Result<Vec<_>>>()?);
+                }
+                tables.push(grid);
+            },
+        }
+    }
+    Ok((blocks, tables))
+}
+
+fn snapshot_paragraph(para: &Paragraph, options: &DiffOptions) -> Result<Block> {
+    let mut block = Block {
+        text: para.text()?,
+        formatting: Vec::new(),
+    };
+    if options.include_formatting {
+        for run in para.runs()? {
+            let span = FormatSpan {
+                chars: run.text()?.chars().count(),
+                bold: run.bold()?.unwrap_or(false),
+                italic: run.italic()?.unwrap_or(false),
+                strikethrough: run.strikethrough()?.unwrap_or(false),
+            };
+            push_span(&mut block.formatting, span);
+        }
+    }
+    Ok(block)
+}
+
+/// Append a span, merging it with the previous one if formatted alike.
+fn push_span(spans: &mut Vec<FormatSpan>, span: FormatSpan) {
+    if span.chars == 0 {
+        return;
+    }
+    match spans.last_mut() {
+        Some(last)
+            if (last.bold, last.italic, last.strikethrough)
+                == (span.bold, span.italic, span.strikethrough) =>
+        {
+            last.chars += span.chars
+        },
+        _ => spans.push(span),
+    }
+}
+
+/// Text as compared under the options.
+fn normalize(text: &str, options: &DiffOptions) -> String {
+    let text = if options.ignore_whitespace {
+        text.split_whitespace().collect::<Vec<_>>().join(" ")
+    } else {
+        text.to_string()
+    };
+    if options.ignore_case {
+        text.to_lowercase()
+    } else {
+        text
+    }
+}
+
+fn text_hash(text: &str, options: &DiffOptions) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    normalize(text, options).hash(&mut hasher);
+    hasher.finish()
+}
+
+fn diff_blocks(old: &[Block], new: &[Block], options: &DiffOptions) -> Vec<ParagraphChange> {
+    let old_keys: Vec<u64> = old.iter().map(|b| text_hash(&b.text, options)).collect();
+    let new_keys: Vec<u64> = new.iter().map(|b| text_hash(&b.text, options)).collect();
+
+    let mut changes = Vec::new();
+    let mut deleted = Vec::new();
+    let mut inserted = Vec::new();
+    for op in align(&old_keys, &new_keys) {
+        match op {
+            Op::Delete(i) => deleted.push(i),
+            Op::Insert(j) => inserted.push(j),
+            Op::Equal(i, j) => {
+                flush_replaced(old, new, &mut deleted, &mut inserted, options, &mut changes);
+                if options.include_formatting && old[i].formatting != new[j].formatting {
+                    changes.push(ParagraphChange {
+                        kind: ChangeKind::Formatting,
+                        old_index: Some(i),
+                        new_index: Some(j),
+                        segments: vec![DiffSegment::Equal(new[j].text.clone())],
+                    });
+                }
+            },
+        }
+    }
+    flush_replaced(old, new, &mut deleted, &mut inserted, options, &mut changes);
+    changes
+}
+
+/// Report a block of deleted and inserted paragraphs.
+///
+/// Each deleted paragraph is paired with the next inserted one that is
+/// similar enough, keeping both in order; pairs become modifications.
+fn flush_replaced(
+    old: &[Block],
+    new: &[Block],
+    deleted: &mut Vec<usize>,
+    inserted: &mut Vec<usize>,
+    options: &DiffOptions,
+    changes: &mut Vec<ParagraphChange>,
+) {
+    let candidates = std::mem::take(inserted);
+    let mut next_candidate = 0;
+    let mut emitted_inserted = 0;
+
+    let push_deleted = |changes: &mut Vec<ParagraphChange>, i: usize| {
+        changes.push(ParagraphChange {
+            kind: ChangeKind::Deleted,
+            old_index: Some(i),
+            new_index: None,
+            segments: vec![DiffSegment::Deleted(old[i].text.clone())],
+        })
+    };
+    let push_inserted = |changes: &mut Vec<ParagraphChange>, j: usize| {
+        changes.push(ParagraphChange {
+            kind: ChangeKind::Inserted,
+            old_index: None,
+            new_index: Some(j),
+            segments: vec![DiffSegment::Inserted(new[j].text.clone())],
+        })
+    };
+
+    for i in deleted.drain(..) {
+        let found = candidates[next_candidate..]
+            .iter()
+            .enumerate()
+            .find_map(|(k, &j)| {
+                let segments = diff_words(&old[i].text, &new[j].text, options);
+                (similarity(&segments) >= MODIFIED_SIMILARITY)
+                    .then_some((next_candidate + k, segments))
+            });
+        let Some((position, segments)) = found else {
+            push_deleted(changes, i);
+            continue;
+        };
+        for &j in &candidates[emitted_inserted..position] {
+            push_inserted(changes, j);
+        }
+        changes.push(ParagraphChange {
+            kind: ChangeKind::Modified,
+            old_index: Some(i),
+            new_index: Some(candidates[position]),
+            segments,
+        });
+        next_candidate = position + 1;
+        emitted_inserted = next_candidate;
+    }
+    for &j in &candidates[emitted_inserted..] {
+        push_inserted(changes, j);
+    }
+}
+
+fn diff_tables(old: &[TableGrid], new: &[TableGrid], options: &DiffOptions) -> Vec<CellChange> {
+    let empty = TableGrid::new();
+    let mut changes = Vec::new();
+    for table in 0..old.len().max(new.len()) {
+        let old_rows = old.get(table).unwrap_or(&empty);
+        let new_rows = new.get(table).unwrap_or(&empty);
+        for row in 0..old_rows.len().max(new_rows.len()) {
+            let old_cells = old_rows.get(row).map(Vec::as_slice).unwrap_or_default();
+            let new_cells = new_rows.get(row).map(Vec::as_slice).unwrap_or_default();
+            for column in 0..old_cells.len().max(new_cells.len()) {
+                let (kind, segments) = match (old_cells.get(column), new_cells.get(column)) {
+                    (Some(a), Some(b)) if normalize(a, options) == normalize(b, options) => {
+                        continue;
+                    },
+                    (Some(a), Some(b)) => (ChangeKind::Modified, diff_words(a, b, options)),
+                    (Some(a), None) => (ChangeKind::Deleted, vec![DiffSegment::Deleted(a.clone())]),
+                    (None, Some(b)) => {
+                        (ChangeKind::Inserted, vec![DiffSegment::Inserted(b.clone())])
+                    },
+                    (None, None) => continue,
+                };
+                changes.push(CellChange {
+                    table,
+                    row,
+                    column,
+                    kind,
+                    segments,
+                });
+            }
+        }
+    }
+    changes
+}
+
+/// Split text into words, whitespace runs and single punctuation characters.
+fn tokenize(text: &str) -> Vec<&str> {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Other,
+    }
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    };
+
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<Class> = None;
+    for (i, c) in text.char_indices() {
+        let current = class(c);
+        if let Some(p) = &prev
+            && (*p != current || current == Class::Other)
+        {
+            tokens.push(&text[start..i]);
+            start = i;
+        }
+        prev = Some(current);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Word-level diff of two texts.
+fn diff_words(old: &str, new: &str, options: &DiffOptions) -> Vec<DiffSegment> {
+    let old_tokens = tokenize(old);
+    let new_tokens = tokenize(new);
+    let key = |token: &str| {
+        if options.ignore_whitespace && token.starts_with(char::is_whitespace) {
+            " ".to_string()
+        } else if options.ignore_case {
+            token.to_lowercase()
+        } else {
+            token.to_string()
+        }
+    };
+    let old_keys: Vec<String> = old_tokens.iter().map(|t| key(t)).collect();
+    let new_keys: Vec<String> = new_tokens.iter().map(|t| key(t)).collect();
+
+    let mut segments: Vec<DiffSegment> = Vec::new();
+    for op in align(&old_keys, &new_keys) {
+        let (text, make): (&str, fn(String) -> DiffSegment) = match op {
+            Op::Equal(_, j) => (new_tokens[j], DiffSegment::Equal),
+            Op::Delete(i) => (old_tokens[i], DiffSegment::Deleted),
+            Op::Insert(j) => (new_tokens[j], DiffSegment::Inserted),
+        };
+        match (segments.last_mut(), make(String::new())) {
+            (Some(DiffSegment::Equal(s)), DiffSegment::Equal(_))
+            | (Some(DiffSegment::Deleted(s)), DiffSegment::Deleted(_))
+            | (Some(DiffSegment::Inserted(s)), DiffSegment::Inserted(_)) => s.push_str(text),
+            _ => segments.push(make(text.to_string())),
+        }
+    }
+    segments
+}
+
+/// Share of non-whitespace characters that two texts have in common.
+fn similarity(segments: &[DiffSegment]) -> f64 {
+    let count = |s: &str| s.chars().filter(|c| !c.is_whitespace()).count();
+    let (mut equal, mut changed) = (0, 0);
+    for segment in segments {
+        match segment {
+            DiffSegment::Equal(s) => equal += 2 * count(s),
+            DiffSegment::Deleted(s) | DiffSegment::Inserted(s) => changed += count(s),
+        }
+    }
+    if equal + changed == 0 {
+        1.0
+    } else {
+        equal as f64 / (equal + changed) as f64
+    }
+}
+
+/// Concatenate the segments visible on one side of a diff.
+fn side_text(segments: &[DiffSegment], new: bool) -> String {
+    segments
+        .iter()
+        .filter_map(|segment| match segment {
+            DiffSegment::Equal(s) => Some(s.as_str()),
+            DiffSegment::Deleted(s) if !new => Some(s.as_str()),
+            DiffSegment::Inserted(s) if new => Some(s.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn render_segments(out: &mut String, segments: &[DiffSegment]) {
+    for segment in segments {
+        match segment {
+            DiffSegment::Equal(s) => push_escaped(out, s),
+            DiffSegment::Deleted(s) => push_marked(out, s, "~~"),
+            DiffSegment::Inserted(s) => push_marked(out, s, "**"),
+        }
+    }
+}
+
+/// Wrap text in a Markdown marker, keeping surrounding whitespace outside it
+/// so that the emphasis is recognized.
+fn push_marked(out: &mut String, text: &str, marker: &str) {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        if marker == "**" {
+            out.push_str(text);
+        }
+        return;
+    }
+    let start = text.len() - text.trim_start().len();
+    out.push_str(&text[..start]);
+    out.push_str(marker);
+    push_escaped(out, trimmed);
+    out.push_str(marker);
+    out.push_str(&text[start + trimmed.len()..]);
+}
+
+fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '*' | '_' | '~' | '`' | '[' | ']' | '<' | '>' | '#'
+        ) {
+            out.push('\\');
+        }
+        if c == '\n' || c == '\r' {
+            out.push(' ');
+        } else {
+            out.push(c);
+        }
+    }
+}
+
+/// One step of an edit script: indexes into the old and new sequences.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+/// Shortest edit script between two sequences.
+fn align<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Op> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+
+    let mut ops: Vec<Op> = (0..prefix).map(|i| Op::Equal(i, i)).collect();
+    ops.extend(myers(a_mid, b_mid).into_iter().map(|op| match op {
+        Op::Equal(i, j) => Op::Equal(i + prefix, j + prefix),
+        Op::Delete(i) => Op::Delete(i + prefix),
+        Op::Insert(j) => Op::Insert(j + prefix),
+    }));
+    let (a_end, b_end) = (prefix + a_mid.len(), prefix + b_mid.len());
+    ops.extend((0..suffix).map(|k| Op::Equal(a_end + k, b_end + k)));
+    ops
+}
+
+/// Myers' O(ND) difference algorithm, keeping the frontier of every step
+/// for the backtrack.
+fn myers<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Op> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (n + m) as usize;
+    if max == 0 {
+        return Vec::new();
+    }
+    let offset = max as isize + 1;
+    let mut v = vec![0isize; 2 * max + 3];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max as isize {
+        if d as usize > MAX_EDIT_DISTANCE {
+            return (0..a.len())
+                .map(Op::Delete)
+                .chain((0..b.len()).map(Op::Insert))
+                .collect();
+        }
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let idx = (offset + k) as usize;
+            let down = k == -d || (k != d && v[idx - 1] < v[idx + 1]);
+            let mut x = if down { v[idx + 1] } else { v[idx - 1] + 1 };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                return backtrack(&trace, n, m);
+            }
+        }
+    }
+    unreachable!("an edit script of length n + m always exists")
+}
+
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<Op> {
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let (prev_x, prev_y) = if d == 0 {
+            (0, 0)
+        } else {
+            let at = |k: isize| v[(k + d) as usize];
+            let down = k == -d || (k != d && at(k - 1) < at(k + 1));
+            let prev_k = if down { k + 1 } else { k - 1 };
+            (at(prev_k), at(prev_k) - prev_k)
+        };
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            ops.push(Op::Equal(x as usize, y as usize));
+        }
+        if d > 0 {
+            if x == prev_x {
+                y -= 1;
+                ops.push(Op::Insert(y as usize));
+            } else {
+                x -= 1;
+                ops.push(Op::Delete(x as usize));
+            }
+        }
+    }
+    ops.reverse();
+    ops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blocks(texts: &[&str]) -> Vec<Block> {
+        texts
+            .iter()
+            .map(|t| Block {
+                text: t.to_string(),
+                formatting: Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Check that an edit script turns `a` into `b`.
+    fn apply(a: &[char], b: &[char], ops: &[Op]) -> String {
+        ops.iter()
+            .filter_map(|op| match *op {
+                Op::Equal(i, j) => {
+                    assert_eq!(a[i], b[j]);
+                    Some(b[j])
+                },
+                Op::Insert(j) => Some(b[j]),
+                Op::Delete(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_align_is_minimal() {
+        let a: Vec<char> = "ABCABBA".chars().collect();
+        let b: Vec<char> = "CBABAC".chars().collect();
+        let ops = align(&a, &b);
+        assert_eq!(apply(&a, &b, &ops), "CBABAC");
+        let edits = ops.iter().filter(|op| !matches!(op, Op::Equal(..))).count();
+        assert_eq!(edits, 5);
+
+        assert!(align::<char>(&[], &[]).is_empty());
+        assert_eq!(align(&['x'], &[]), [Op::Delete(0)]);
+    }
+
+    #[test]
+    fn test_diff_words() {
+        let segments = diff_words(
+            "The quick brown fox.",
+            "The slow brown fox!",
+            &DiffOptions::new(),
+        );
+        assert_eq!(
+            segments,
+            [
+                DiffSegment::Equal("The ".into()),
+                DiffSegment::Deleted("quick".into()),
+                DiffSegment::Inserted("slow".into()),
+                DiffSegment::Equal(" brown fox".into()),
+                DiffSegment::Deleted(".".into()),
+                DiffSegment::Inserted("!".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_blocks() {
+        let old = blocks(&["Title", "Price is 10 USD.", "Old clause", "Signed"]);
+        let new = blocks(&["Title", "New intro", "Price is 12 USD.", "Signed"]);
+        let changes = diff_blocks(&old, &new, &DiffOptions::new());
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.kind, c.old_index, c.new_index))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (ChangeKind::Inserted, None, Some(1)),
+                (ChangeKind::Modified, Some(1), Some(2)),
+                (ChangeKind::Deleted, Some(2), None),
+            ]
+        );
+        assert_eq!(changes[1].old_text(), "Price is 10 USD.");
+        assert_eq!(changes[1].new_text(), "Price is 12 USD.");
+
+        let diff = DocumentDiff {
+            paragraphs: changes,
+            cells: Vec::new(),
+        };
+        assert_eq!(
+            diff.to_markdown(),
+            "- Paragraph 2 (inserted): **New intro**\n\
+             - Paragraph 3: Price is ~~10~~**12** USD.\n\
+             - Paragraph 3 (deleted): ~~Old clause~~\n"
+        );
+    }
+
+    #[test]
+    fn test_diff_options() {
+        let old = blocks(&["Hello  World"]);
+        let new = blocks(&["hello world "]);
+        assert!(!diff_blocks(&old, &new, &DiffOptions::new()).is_empty());
+        let lenient = DiffOptions::new()
+            .with_ignore_case(true)
+            .with_ignore_whitespace(true);
+        assert!(diff_blocks(&old, &new, &lenient).is_empty());
+
+        let mut old = blocks(&["Bold"]);
+        let mut new = blocks(&["Bold"]);
+        old[0].formatting = vec![FormatSpan {
+            chars: 4,
+            bold: false,
+            italic: false,
+            strikethrough: false,
+        }];
+        new[0].formatting = vec![FormatSpan {
+            bold: true,
+            ..old[0].formatting[0]
+        }];
+        assert!(diff_blocks(&old, &new, &DiffOptions::new()).is_empty());
+        let changes = diff_blocks(&old, &new, &DiffOptions::new().with_formatting(true));
+        assert_eq!(changes[0].kind, ChangeKind::Formatting);
+    }
+
+    #[test]
+    fn test_diff_tables() {
+        let old = vec![vec![vec!["Item".to_string(), "10".to_string()]]];
+        let new = vec![vec![
+            vec!["Item".to_string(), "12".to_string()],
+            vec!["Tax".to_string()],
+        ]];
+        let changes = diff_tables(&old, &new, &DiffOptions::new());
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| (c.row, c.column, c.kind, c.new_text()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                (0, 1, ChangeKind::Modified, "12".to_string()),
+                (1, 0, ChangeKind::Inserted, "Tax".to_string()),
+            ]
+        );
+    }
+
+    #[cfg(feature = "ooxml")]
+    #[test]
+    fn test_diff_docx_files() {
+        use crate::ooxml::docx::Package;
+
+        let dir = std::env::temp_dir().join(format!("litchi_diff_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let write = |name: &str, paragraphs: &[(&str, bool)], cell: &str| {
+            let mut pkg = Package::new().unwrap();
+            let doc = pkg.document_mut().unwrap();
+            for &(text, bold) in paragraphs {
+                doc.add_paragraph().add_run_with_text(text).bold(bold);
+            }
+            doc.add_table(1, 1).cell(0, 0).unwrap().set_text(cell);
+            let path = dir.join(name);
+            pkg.save(&path).unwrap();
+            Document::open(&path).unwrap()
+        };
+
+        let old = write(
+            "old.docx",
+            &[("Terms", false), ("The fee is 100 EUR.", false)],
+            "Net 30",
+        );
+        let new = write(
+            "new.docx",
+            &[("Terms", true), ("The fee is 120 EUR.", false)],
+            "Net 60",
+        );
+        let options = DiffOptions::new().with_formatting(true);
+        let changes = diff(&old, &new, &options).unwrap();
+        std::fs::remove_dir_all(&dir).ok();
+
+        let kinds: Vec<_> = changes.paragraphs.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, [ChangeKind::Formatting, ChangeKind::Modified]);
+        assert_eq!(changes.paragraphs[1].new_text(), "The fee is 120 EUR.");
+        assert_eq!(changes.cells.len(), 1);
+        assert_eq!(changes.cells[0].old_text(), "Net 30");
+
+        let yaml = serde_saphyr::to_string(&changes).unwrap();
+        assert!(yaml.contains("kind: formatting"));
+    }
+}
@@ -1,8 +1,19 @@
 //! Word document implementation.
 
+#[cfg(feature = "ole")]
+use super::types::DocFile;
 use super::types::DocumentImpl;
-use super::{Paragraph, Table};
-use crate::common::{Error, Result};
+use super::{
+    DocumentStatistics, EmbeddedObject, FloatingText, HeaderFooter, HeaderFooterType, ImageRef,
+    Note, NoteKind, OutlineEntry, PageMargins, PageOrientation, Paragraph, Revision, RevisionMode,
+    RevisionType, Section, Table, TextOptions,
+};
+use crate::common::search::{MatchLocation, Matcher, SearchOptions, TextMatch, find_in_runs};
+use crate::common::statistics::TextCounts;
+use crate::common::walk::TextEvent;
+#[cfg(any(feature = "ole", feature = "iwa", feature = "rtf", feature = "odf"))]
+use crate::common::walk::{visit, visit_lines};
+use crate::common::{Error, OpenOptions, Protection, Result};
 
 #[cfg(feature = "ole")]
 use crate::ole;
@@ -10,6 +21,7 @@ use crate::ole;
 #[cfg(feature = "ooxml")]
 use crate::ooxml;
 
+use std::ops::ControlFlow;
 use std::path::Path;
 
 /// A Word document.
@@ -80,6 +92,15 @@ impl Document {
     /// # Ok::<(), litchi::common::Error>(())
     /// ```
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        // Legacy .doc files are read from disk as their parts are needed
+        #[cfg(feature = "ole")]
+        if let Some(ole_file) = crate::common::detection::open_legacy_file(
+            path.as_ref(),
+            crate::common::detection::FileFormat::Doc,
+        )? {
+            return Self::from_ole_file(ole_file);
+        }
+
         // Read file into memory and use smart detection for single-pass parsing
         // This is faster than the old approach of detecting first then parsing again
         let bytes = std::fs::read(path.as_ref())?;
@@ -118,37 +139,141 @@ impl Document {
     /// - **Single-pass parsing**: Format detection reuses the parsed structure (40-60% faster)
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
         // Use smart detection to parse only once
-        use crate::common::detection::{DetectedFormat, detect_format_smart};
+        use crate::common::detection::detected::detect_format_checked;
+
+        let detected = detect_format_checked(bytes)?;
+        Self::from_detected(detected)
+    }
+
+    /// Open a Word document with the given options.
+    ///
+    /// With [`OpenOptions::recovery`] set, a damaged .docx that Word would
+    /// offer to repair, such as a truncated file or one without
+    /// `[Content_Types].xml`, is opened with whatever content could be
+    /// recovered. Check [`Document::was_recovered`] to warn users about it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::{Document, OpenOptions};
+    ///
+    /// let options = OpenOptions::new().with_recovery(true);
+    /// let doc = Document::open_with_options("damaged.docx", &options)?;
+    /// if doc.was_recovered() {
+    ///     eprintln!("The document was damaged; some content may be missing");
+    /// }
+    /// println!("{}", doc.text()?);
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn open_with_options<P: AsRef<Path>>(path: P, options: &OpenOptions) -> Result<Self> {
+        let bytes = std::fs::read(path.as_ref())?;
+        Self::from_bytes_with_options(bytes, options)
+    }
+
+    /// Create a Document from a byte buffer with the given options.
+    ///
+    /// See [`Document::open_with_options`] for details.
+    pub fn from_bytes_with_options(bytes: Vec<u8>, options: &OpenOptions) -> Result<Self> {
+        use crate::common::detection::detected::detect_format_with_options;
+
+        Self::from_detected(detect_format_with_options(bytes, options)?)
+    }
+
+    /// Whether the document was damaged and repaired when it was opened.
+    ///
+    /// Only documents opened with [`OpenOptions::recovery`] are ever
+    /// repaired; content that could not be recovered is missing from them.
+    pub fn was_recovered(&self) -> bool {
+        #[cfg(feature = "ooxml")]
+        if let Some(package) = &self._package {
+            return package.opc_package().was_recovered();
+        }
+        false
+    }
+
+    /// Open a password-protected Word document.
+    ///
+    /// Encrypted .docx files (Standard 2007 or Agile encryption) are decrypted
+    /// in memory. Files that are not encrypted open normally and the password is
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::IncorrectPassword`] if the password does not unlock the file
+    /// - [`Error::UnsupportedEncryption`] if the encryption scheme is not supported
+    /// - [`Error::Encrypted`] for password-protected legacy .doc files, whose RC4
+    ///   encryption cannot be decrypted
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open_with_password("protected.docx", "secret")?;
+    /// println!("{}", doc.text()?);
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    #[cfg(feature = "ooxml_encryption")]
+    pub fn open_with_password<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        let bytes = std::fs::read(path.as_ref())?;
+        Self::from_bytes_with_password(bytes, password)
+    }
+
+    /// Create a Document from the bytes of a possibly password-protected file.
+    ///
+    /// See [`Document::open_with_password`] for details.
+    #[cfg(feature = "ooxml_encryption")]
+    pub fn from_bytes_with_password(bytes: Vec<u8>, password: &str) -> Result<Self> {
+        use crate::common::detection::detect_format_with_password;
+
+        Self::from_detected(detect_format_with_password(bytes, password)?)
+    }
+
+    /// Create a Document from an OLE file already detected as a .doc file.
+    ///
+    /// Only the metadata and the File Information Block are read here; the
+    /// document itself is parsed on first use.
+    #[cfg(feature = "ole")]
+    fn from_ole_file(ole_file: ole::OleFile<ole::OleSource>) -> Result<Self> {
+        let mut package = ole::doc::Package::from_ole_file(ole_file).map_err(Error::from)?;
+        package.validate().map_err(Error::from)?;
+
+        // Extract metadata from the OLE file
+        let metadata = package
+            .ole_file()
+            .get_metadata()
+            .map(|m| m.into())
+            .unwrap_or_default();
+
+        Ok(Self {
+            inner: DocumentImpl::Doc(DocFile::new(package), metadata),
+            #[cfg(feature = "ooxml")]
+            _package: None,
+        })
+    }
+
+    /// Create a document from an already detected format.
+    ///
+    /// Shared by [`Document::from_bytes`] and [`crate::open_from_bytes`] so the
+    /// file is only parsed once.
+    #[allow(unused_mut)] // Only mutated for OLE encryption checks
+    pub(crate) fn from_detected(
+        mut detected: crate::common::detection::DetectedFormat,
+    ) -> Result<Self> {
+        use crate::common::detection::DetectedFormat;
 
-        let detected = detect_format_smart(bytes).ok_or(Error::NotOfficeFile)?;
+        #[cfg(feature = "ole")]
+        crate::common::detection::check_legacy_encryption(&mut detected)?;
 
         match detected {
             #[cfg(feature = "ole")]
             DetectedFormat::Doc(ole_file) => {
-                // OLE file already parsed - reuse it!
-                let mut package =
-                    ole::doc::Package::from_ole_file(ole_file).map_err(Error::from)?;
-                let doc = package.document().map_err(Error::from)?;
-
-                // Extract metadata from the OLE file
-                let metadata = package
-                    .ole_file()
-                    .get_metadata()
-                    .map(|m| m.into())
-                    .unwrap_or_default();
-
-                Ok(Self {
-                    inner: DocumentImpl::Doc(doc, metadata),
-                    #[cfg(feature = "ooxml")]
-                    _package: None,
-                })
+                Self::from_ole_file(ole_file.map_reader(ole::OleSource::Memory))
             },
             #[cfg(feature = "rtf")]
             DetectedFormat::Rtf(bytes) => {
-                let text = String::from_utf8(bytes)
-                    .map_err(|e| Error::ParseError(format!("Invalid UTF-8 in RTF: {}", e)))?;
-
-                let doc = crate::rtf::RtfDocument::parse(&text).map_err(|e| {
+                // RTF is code-page encoded and may be compressed, which `from_bytes` handles
+                let doc = crate::rtf::RtfDocument::from_bytes(&bytes).map_err(|e| {
                     Error::ParseError(format!("Failed to parse RTF document: {}", e))
                 })?;
 
@@ -197,9 +322,7 @@ impl Document {
             },
             #[cfg(feature = "odf")]
             DetectedFormat::Odt(data) => {
-                let doc = crate::odf::Document::from_bytes(data).map_err(|e| {
-                    Error::ParseError(format!("Failed to parse ODT document from bytes: {}", e))
-                })?;
+                let doc = crate::odf::Document::from_bytes(data)?;
 
                 Ok(Self {
                     inner: DocumentImpl::Odt(doc),
@@ -207,6 +330,10 @@ impl Document {
                     _package: None,
                 })
             },
+            #[cfg(feature = "ole")]
+            DetectedFormat::Encrypted(_) => Err(Error::Encrypted(
+                "password-protected OOXML package".to_string(),
+            )),
             // Handle mismatched formats
             #[allow(unreachable_patterns)]
             _ => Err(Error::InvalidFormat(
@@ -232,7 +359,7 @@ impl Document {
     pub fn text(&self) -> Result<String> {
         match &self.inner {
             #[cfg(feature = "ole")]
-            DocumentImpl::Doc(doc, _) => doc.text().map_err(Error::from),
+            DocumentImpl::Doc(doc, _) => doc.document()?.text().map_err(Error::from),
             #[cfg(feature = "ooxml")]
             DocumentImpl::Docx(doc, _) => doc.text().map_err(Error::from),
             #[cfg(feature = "iwa")]
@@ -242,12 +369,143 @@ impl Document {
             #[cfg(feature = "rtf")]
             DocumentImpl::Rtf(doc) => Ok(doc.text()),
             #[cfg(feature = "odf")]
-            DocumentImpl::Odt(doc) => doc
-                .text()
-                .map_err(|e| Error::ParseError(format!("Failed to extract text from ODT: {}", e))),
+            DocumentImpl::Odt(doc) => doc.text(),
+        }
+    }
+
+    /// Get all text content of the document, as `options` asks.
+    ///
+    /// Paragraphs are separated by [`TextOptions::paragraph_separator`], and
+    /// white space within them is kept or collapsed as
+    /// [`TextOptions::preserve_whitespace`] asks. With
+    /// [`TextOptions::floating_text`], the text of each text box or shape
+    /// follows the paragraph it is anchored to, one line per paragraph of the
+    /// shape; see [`Document::floating_text`]. With
+    /// [`TextOptions::bidi_isolates`], runs whose direction differs from
+    /// their paragraph's are wrapped in Unicode directional isolates.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    /// use litchi::document::TextOptions;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// let text = doc.text_with_options(&TextOptions::new().with_floating_text(true))?;
+    /// println!("{}", text);
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn text_with_options(&self, options: &TextOptions) -> Result<String> {
+        let mut floating: std::collections::HashMap<usize, Vec<String>> =
+            std::collections::HashMap::new();
+        if options.floating_text {
+            for shape in self.floating_text()? {
+                floating
+                    .entry(shape.paragraph_index())
+                    .or_default()
+                    .push(shape.text()?);
+            }
+        }
+
+        // SmartArt text is part of the body text, as for `Document::text`
+        #[cfg(feature = "ooxml")]
+        let mut diagrams: std::collections::HashMap<usize, Vec<String>> =
+            std::collections::HashMap::new();
+        #[cfg(feature = "ooxml")]
+        for (index, smartart) in self.smartart_by_paragraph()? {
+            let text = smartart.text();
+            if !text.is_empty() {
+                diagrams.entry(index).or_default().push(text);
+            }
+        }
+
+        let mut lines = Vec::new();
+        for (index, para) in self.paragraphs()?.iter().enumerate() {
+            // Join run texts, since .doc paragraphs keep their text in the runs
+            let runs: Vec<(String, bool)> = para
+                .runs()?
+                .iter()
+                .map(|run| Ok((run.text()?, options.bidi_isolates && run.is_rtl()?)))
+                .collect::<Result<_>>()?;
+            let rtl = options.bidi_isolates && para.direction()?.is_rtl();
+            lines.push(options.paragraph_text(&options.join_runs(rtl, &runs)));
+            #[cfg(feature = "ooxml")]
+            if let Some(texts) = diagrams.remove(&index) {
+                lines.extend(texts.iter().map(|text| options.paragraph_text(text)));
+            }
+            if let Some(texts) = floating.remove(&index) {
+                lines.extend(texts.iter().map(|text| options.paragraph_text(text)));
+            }
+        }
+        Ok(lines.join(&options.paragraph_separator))
+    }
+
+    /// Report the body text to `visitor` in reading order, stopping as soon
+    /// as it returns [`ControlFlow::Break`].
+    ///
+    /// Paragraphs and tables are reported as [`TextEvent`]s. For .docx the
+    /// document part is parsed as the walk proceeds, so nothing after the
+    /// point where the visitor stops is parsed. Other formats report
+    /// paragraphs and tables that were read when the document was opened.
+    ///
+    /// Returns whether the visitor stopped the walk.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    /// use litchi::common::walk::TextEvent;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let doc = Document::open("archive.doc")?;
+    /// let found = doc.walk_text(|event| match event {
+    ///     TextEvent::Text(text) if text.contains("password") => ControlFlow::Break(()),
+    ///     _ => ControlFlow::Continue(()),
+    /// })?;
+    /// println!("found: {}", found.is_break());
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn walk_text<F>(&self, mut visitor: F) -> Result<ControlFlow<()>>
+    where
+        F: FnMut(TextEvent<'_>) -> ControlFlow<()>,
+    {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => walk_doc(doc, &mut visitor),
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => doc.walk_text(&mut visitor).map_err(Error::from),
+            #[cfg(feature = "iwa")]
+            DocumentImpl::Pages(_) => walk_elements(&self.elements()?, &mut visitor),
+            #[cfg(feature = "rtf")]
+            DocumentImpl::Rtf(_) => walk_elements(&self.elements()?, &mut visitor),
+            #[cfg(feature = "odf")]
+            DocumentImpl::Odt(_) => walk_elements(&self.elements()?, &mut visitor),
         }
     }
 
+    /// Count the words, characters and paragraphs of the document.
+    ///
+    /// The counts are computed from the body text in the same way for every
+    /// format. The page count, and the word and character counts that the
+    /// saving application stored in the file, are returned alongside them
+    /// when the file has them; see [`DocumentStatistics`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.odt")?;
+    /// let stats = doc.statistics()?;
+    /// println!("Words: {}", stats.word_count());
+    /// println!("Paragraphs: {}", stats.paragraph_count());
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn statistics(&self) -> Result<DocumentStatistics> {
+        let counts = TextCounts::from_walk(|visitor| self.walk_text(visitor))?;
+        Ok(DocumentStatistics::new(counts, &self.metadata()?))
+    }
+
     /// Get the number of paragraphs in the document.
     ///
     /// # Examples
@@ -263,23 +521,18 @@ impl Document {
     pub fn paragraph_count(&self) -> Result<usize> {
         match &self.inner {
             #[cfg(feature = "ole")]
-            DocumentImpl::Doc(doc, _) => doc.paragraph_count().map_err(Error::from),
+            DocumentImpl::Doc(doc, _) => doc.document()?.paragraph_count().map_err(Error::from),
             #[cfg(feature = "ooxml")]
             DocumentImpl::Docx(doc, _) => doc.paragraph_count().map_err(Error::from),
             #[cfg(feature = "iwa")]
-            DocumentImpl::Pages(doc) => {
-                // Pages documents are organized by sections
-                let sections = doc
-                    .sections()
-                    .map_err(|e| Error::ParseError(format!("Failed to get sections: {}", e)))?;
-                Ok(sections.iter().map(|s| s.paragraphs.len()).sum())
-            },
+            DocumentImpl::Pages(doc) => doc
+                .paragraphs()
+                .map(|paras| paras.len())
+                .map_err(Error::from),
             #[cfg(feature = "rtf")]
             DocumentImpl::Rtf(doc) => Ok(doc.paragraph_count()),
             #[cfg(feature = "odf")]
-            DocumentImpl::Odt(doc) => doc
-                .paragraph_count()
-                .map_err(|e| Error::ParseError(format!("Failed to get paragraph count: {}", e))),
+            DocumentImpl::Odt(doc) => doc.paragraph_count(),
         }
     }
 
@@ -300,6 +553,7 @@ impl Document {
         match &self.inner {
             #[cfg(feature = "ole")]
             DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
                 let paras = doc.paragraphs().map_err(Error::from)?;
                 Ok(paras.into_iter().map(Paragraph::Doc).collect())
             },
@@ -310,20 +564,8 @@ impl Document {
             },
             #[cfg(feature = "iwa")]
             DocumentImpl::Pages(doc) => {
-                // Pages documents have sections, each with paragraphs
-                let sections = doc
-                    .sections()
-                    .map_err(|e| Error::ParseError(format!("Failed to get sections: {}", e)))?;
-                let paragraphs: Vec<_> = sections
-                    .iter()
-                    .flat_map(|section| {
-                        section
-                            .paragraphs
-                            .iter()
-                            .map(|text| Paragraph::Pages(text.clone()))
-                    })
-                    .collect();
-                Ok(paragraphs)
+                let paras = doc.paragraphs().map_err(Error::from)?;
+                Ok(paras.into_iter().map(Paragraph::Pages).collect())
             },
             #[cfg(feature = "rtf")]
             DocumentImpl::Rtf(doc) => {
@@ -350,14 +592,91 @@ impl Document {
             },
             #[cfg(feature = "odf")]
             DocumentImpl::Odt(doc) => {
-                let paras = doc
-                    .paragraphs()
-                    .map_err(|e| Error::ParseError(format!("Failed to get paragraphs: {}", e)))?;
+                let paras = doc.paragraphs()?;
                 Ok(paras.into_iter().map(Paragraph::Odt).collect())
             },
         }
     }
 
+    /// Get the paragraphs with indices in `range`.
+    ///
+    /// Returns the same paragraphs as `paragraphs()[range]`; indices past the
+    /// last paragraph are ignored. For .docx files only the requested
+    /// paragraphs are parsed, so reading the first page of a long document
+    /// does not pay for the rest of it. Other formats parse every paragraph.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("long.docx")?;
+    /// for para in doc.paragraphs_range(0..50)? {
+    ///     println!("{}", para.text()?);
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn paragraphs_range(&self, range: std::ops::Range<usize>) -> Result<Vec<Paragraph>> {
+        #[cfg(feature = "ooxml")]
+        #[allow(irrefutable_let_patterns)]
+        if let DocumentImpl::Docx(doc, _) = &self.inner {
+            let paras = doc.paragraphs_range(range).map_err(Error::from)?;
+            return Ok(paras.into_iter().map(Paragraph::Docx).collect());
+        }
+
+        let mut paras = self.paragraphs()?;
+        paras.truncate(range.end);
+        Ok(paras.split_off(range.start.min(paras.len())))
+    }
+
+    /// Find every occurrence of `pattern` in the document paragraphs.
+    ///
+    /// Each paragraph is searched as the concatenated text of its runs, so a
+    /// match split across differently formatted runs is still found; its
+    /// [`segments`](TextMatch::segments) list the run pieces it covers.
+    /// Paragraphs without runs are searched as a single run.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the pattern is an invalid regular expression, or a regular
+    /// expression is requested without the `regex` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    /// use litchi::common::search::{MatchLocation, SearchOptions};
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// let options = SearchOptions::new().with_whole_word(true);
+    /// for m in doc.find("litchi", &options)? {
+    ///     if let MatchLocation::Paragraph { index } = m.location() {
+    ///         println!("paragraph {}: {}", index, m.snippet());
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn find(&self, pattern: &str, options: &SearchOptions) -> Result<Vec<TextMatch>> {
+        let matcher = Matcher::new(pattern, options)?;
+        let mut matches = Vec::new();
+
+        for (index, para) in self.paragraphs()?.iter().enumerate() {
+            let location = MatchLocation::Paragraph { index };
+            let runs = para
+                .runs()?
+                .iter()
+                .map(|run| run.text())
+                .collect::<Result<Vec<_>>>()?;
+            if runs.is_empty() {
+                matches.extend(find_in_runs(&matcher, &[para.text()?], location));
+            } else {
+                matches.extend(find_in_runs(&matcher, &runs, location));
+            }
+        }
+
+        Ok(matches)
+    }
+
     /// Get an iterator over tables in the document.
     ///
     /// # Examples
@@ -375,6 +694,7 @@ impl Document {
         match &self.inner {
             #[cfg(feature = "ole")]
             DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
                 let tables = doc.tables().map_err(Error::from)?;
                 Ok(tables.into_iter().map(Table::Doc).collect())
             },
@@ -387,10 +707,12 @@ impl Document {
                     .collect())
             },
             #[cfg(feature = "iwa")]
-            DocumentImpl::Pages(_doc) => {
-                // Pages tables are not currently supported in the paragraph/table extraction API
-                // Tables in Pages are embedded as structured data which requires different extraction
-                Ok(Vec::new())
+            DocumentImpl::Pages(doc) => {
+                let tables = doc.tables().map_err(Error::from)?;
+                Ok(tables
+                    .into_iter()
+                    .map(|t| Table::Pages(Box::new(t)))
+                    .collect())
             },
             #[cfg(feature = "rtf")]
             DocumentImpl::Rtf(doc) => {
@@ -416,9 +738,7 @@ impl Document {
             },
             #[cfg(feature = "odf")]
             DocumentImpl::Odt(doc) => {
-                let tables = doc
-                    .tables()
-                    .map_err(|e| Error::ParseError(format!("Failed to get tables: {}", e)))?;
+                let tables = doc.tables()?;
                 Ok(tables.into_iter().map(Table::Odt).collect())
             },
         }
@@ -460,25 +780,27 @@ impl Document {
     pub fn elements(&self) -> Result<Vec<super::DocumentElement>> {
         match &self.inner {
             #[cfg(feature = "ole")]
-            DocumentImpl::Doc(doc, _) => doc.elements().map_err(Error::from),
+            DocumentImpl::Doc(doc, _) => doc.document()?.elements().map_err(Error::from),
             #[cfg(feature = "ooxml")]
             DocumentImpl::Docx(doc, _) => doc.elements().map_err(Error::from),
             #[cfg(feature = "iwa")]
             DocumentImpl::Pages(doc) => {
                 use super::DocumentElement;
-                // Pages documents have sections with paragraphs
-                // Tables are not currently supported in the extraction API
-                let sections = doc
-                    .sections()
-                    .map_err(|e| Error::ParseError(format!("Failed to get sections: {}", e)))?;
-                let elements: Vec<_> = sections
-                    .iter()
-                    .flat_map(|section| {
-                        section.paragraphs.iter().map(|text| {
-                            DocumentElement::Paragraph(Box::new(Paragraph::Pages(text.clone())))
-                        })
-                    })
+
+                // Tables are anchored in the body text but not resolved to
+                // their position, so they follow the paragraphs
+                let paras = doc.paragraphs().map_err(Error::from)?;
+                let tables = doc.tables().map_err(Error::from)?;
+
+                let mut elements: Vec<_> = paras
+                    .into_iter()
+                    .map(|p| DocumentElement::Paragraph(Box::new(Paragraph::Pages(p))))
                     .collect();
+                elements.extend(
+                    tables
+                        .into_iter()
+                        .map(|t| DocumentElement::Table(Box::new(Table::Pages(Box::new(t))))),
+                );
                 Ok(elements)
             },
             #[cfg(feature = "rtf")]
@@ -536,9 +858,7 @@ impl Document {
                 use crate::odf::elements::text::Paragraph as ElementParagraph;
 
                 // Get ODF-specific elements and convert to unified API types
-                let odf_elements = doc
-                    .elements()
-                    .map_err(|e| Error::ParseError(format!("Failed to get elements: {}", e)))?;
+                let odf_elements = doc.elements()?;
 
                 let mut elements = Vec::new();
                 for element in odf_elements {
@@ -575,94 +895,1310 @@ impl Document {
         }
     }
 
-    /// Get document metadata.
+    /// Get all footnotes in the document.
     ///
-    /// Extracts metadata from the document such as title, author, creation date, etc.
-    /// For OLE (.doc) files, this reads from SummaryInformation and DocumentSummaryInformation streams.
-    /// For OOXML (.docx) files, this reads from core properties (currently not implemented).
+    /// Supported for .doc and .docx files; other formats return no notes.
+    /// Separator notes are skipped.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
     /// use litchi::Document;
     ///
-    /// let doc = Document::open("document.doc")?;
-    /// let metadata = doc.metadata()?;
-    /// if let Some(title) = &metadata.title {
-    ///     println!("Title: {}", title);
+    /// let doc = Document::open("document.docx")?;
+    /// for note in doc.footnotes()? {
+    ///     println!("Footnote {}: {}", note.id(), note.text()?);
     /// }
     /// # Ok::<(), litchi::common::Error>(())
     /// ```
-    pub fn metadata(&self) -> Result<crate::common::Metadata> {
+    pub fn footnotes(&self) -> Result<Vec<Note>> {
+        self.notes(NoteKind::Footnote)
+    }
+
+    /// Get all endnotes in the document.
+    ///
+    /// See [`Document::footnotes`] for details.
+    pub fn endnotes(&self) -> Result<Vec<Note>> {
+        self.notes(NoteKind::Endnote)
+    }
+
+    fn notes(&self, kind: NoteKind) -> Result<Vec<Note>> {
         match &self.inner {
             #[cfg(feature = "ole")]
-            DocumentImpl::Doc(_, metadata) => Ok(metadata.clone()),
-            #[cfg(feature = "ooxml")]
-            DocumentImpl::Docx(_, metadata) => Ok(metadata.clone()),
-            #[cfg(feature = "iwa")]
-            DocumentImpl::Pages(doc) => {
-                // Extract metadata from Pages bundle metadata
-                let bundle_metadata = doc.bundle().metadata();
-                let mut metadata = crate::common::Metadata::default();
-
-                // Extract title from properties
-                if let Some(title) = bundle_metadata.get_property_string("Title") {
-                    metadata.title = Some(title);
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                let notes = match kind {
+                    NoteKind::Footnote => doc.footnotes(),
+                    NoteKind::Endnote => doc.endnotes(),
                 }
+                .map_err(Error::from)?;
 
-                // Extract author from properties
-                if let Some(author) = bundle_metadata.get_property_string("Author") {
-                    metadata.author = Some(author);
+                Ok(notes
+                    .into_iter()
+                    .zip(1..)
+                    .map(|(note, id)| {
+                        Note::new(
+                            kind,
+                            id,
+                            Some(doc.paragraph_index_at(note.reference_position)),
+                            note.paragraphs.into_iter().map(Paragraph::Doc).collect(),
+                        )
+                    })
+                    .collect())
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => {
+                let notes = match kind {
+                    NoteKind::Footnote => doc.footnotes(),
+                    NoteKind::Endnote => doc.endnotes(),
+                }
+                .map_err(Error::from)?;
+                if notes.is_empty() {
+                    return Ok(Vec::new());
                 }
 
-                // Extract document identifier
-                if let Some(doc_id) = bundle_metadata.document_identifier() {
-                    metadata.description = Some(format!("Document ID: {}", doc_id));
+                // Locate the body paragraph carrying each reference mark
+                let mut anchors = std::collections::HashMap::new();
+                for (index, para) in doc.paragraphs().map_err(Error::from)?.iter().enumerate() {
+                    let ids = match kind {
+                        NoteKind::Footnote => para.footnote_reference_ids(),
+                        NoteKind::Endnote => para.endnote_reference_ids(),
+                    }
+                    .map_err(Error::from)?;
+                    for id in ids {
+                        anchors.entry(id).or_insert(index);
+                    }
                 }
 
-                Ok(metadata)
-            },
-            #[cfg(feature = "rtf")]
-            DocumentImpl::Rtf(_doc) => {
-                // RTF doesn't have standard metadata in the same way
-                // Metadata would need to be parsed from \info group
-                Ok(crate::common::Metadata::default())
+                notes
+                    .into_iter()
+                    .map(|note| {
+                        let paragraphs = note.paragraphs().map_err(Error::from)?;
+                        Ok(Note::new(
+                            kind,
+                            note.id(),
+                            anchors.get(&note.id()).copied(),
+                            paragraphs.into_iter().map(Paragraph::Docx).collect(),
+                        ))
+                    })
+                    .collect()
             },
-            #[cfg(feature = "odf")]
-            DocumentImpl::Odt(doc) => doc
-                .metadata()
-                .map_err(|e| Error::ParseError(format!("Failed to get metadata: {}", e))),
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
         }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::PathBuf;
 
-    fn test_data_path() -> PathBuf {
-        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data")
+    /// Get the headers of all sections.
+    ///
+    /// Returns the headers each section declares, ordered by section and
+    /// tagged with their [`HeaderFooterType`]. A section without a header of
+    /// some type continues the header of the previous section, so it is only
+    /// reported once. Supported for .doc and .docx files; other formats
+    /// return no headers.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// for header in doc.headers()? {
+    ///     println!("Section {}: {}", header.section_index(), header.text()?);
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn headers(&self) -> Result<Vec<HeaderFooter>> {
+        self.headers_footers(true)
     }
 
-    #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_open_docx() {
-        let path = test_data_path().join("ooxml/docx/FancyFoot.docx");
-        let doc = Document::open(&path);
-        assert!(doc.is_ok(), "Failed to open DOCX file: {:?}", doc.err());
+    /// Get the footers of all sections.
+    ///
+    /// See [`Document::headers`] for details.
+    pub fn footers(&self) -> Result<Vec<HeaderFooter>> {
+        self.headers_footers(false)
     }
 
-    #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_open_doc() {
-        let path = test_data_path().join("ole/doc/FancyFoot.doc");
-        let doc = Document::open(&path);
-        assert!(doc.is_ok(), "Failed to open DOC file: {:?}", doc.err());
-    }
+    fn headers_footers(&self, headers: bool) -> Result<Vec<HeaderFooter>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                use ole::doc::parts::headers::HeaderFooterType as DocType;
 
-    #[test]
-    #[cfg(feature = "rtf")]
+                let stories = if headers {
+                    doc.headers()
+                } else {
+                    doc.footers()
+                }
+                .map_err(Error::from)?;
+
+                Ok(stories
+                    .into_iter()
+                    .map(|story| {
+                        let hf_type = match story.header_footer_type {
+                            DocType::FirstPageHeader | DocType::FirstPageFooter => {
+                                HeaderFooterType::First
+                            },
+                            DocType::EvenPageHeader | DocType::EvenPageFooter => {
+                                HeaderFooterType::Even
+                            },
+                            DocType::OddPageHeader | DocType::OddPageFooter => {
+                                HeaderFooterType::Default
+                            },
+                        };
+                        HeaderFooter::new(
+                            story.section_index,
+                            hf_type,
+                            story.paragraphs.into_iter().map(Paragraph::Doc).collect(),
+                        )
+                    })
+                    .collect())
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => {
+                use ooxml::docx::WdHeaderFooter;
+
+                let sections = if headers {
+                    doc.headers_by_section()
+                } else {
+                    doc.footers_by_section()
+                }
+                .map_err(Error::from)?;
+
+                let mut result = Vec::new();
+                for (section_index, parts) in sections.into_iter().enumerate() {
+                    for (wd_type, part) in parts {
+                        let hf_type = match wd_type {
+                            WdHeaderFooter::Primary => HeaderFooterType::Default,
+                            WdHeaderFooter::FirstPage => HeaderFooterType::First,
+                            WdHeaderFooter::EvenPage => HeaderFooterType::Even,
+                        };
+                        let paragraphs = part.paragraphs().map_err(Error::from)?;
+                        result.push(HeaderFooter::new(
+                            section_index,
+                            hf_type,
+                            paragraphs.into_iter().map(Paragraph::Docx).collect(),
+                        ));
+                    }
+                }
+                Ok(result)
+            },
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the OLE objects embedded in the document.
+    ///
+    /// Objects are read from the `embeddings` parts of .docx files and from
+    /// the `ObjectPool` storage of .doc files; other formats return no
+    /// objects. Each object carries its class, its raw bytes, the payload of
+    /// packaged files and embedded Office Open XML documents, and its preview
+    /// image.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.doc")?;
+    /// for object in doc.embedded_objects()? {
+    ///     println!("{:?} ({} bytes)", object.prog_id(), object.data().len());
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn embedded_objects(&self) -> Result<Vec<EmbeddedObject>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => Ok(doc
+                .document()?
+                .object_pool()
+                .iter()
+                .map(|(_, data)| EmbeddedObject::new(None, data.clone(), None))
+                .collect()),
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => Ok(doc
+                .ole_objects()
+                .map_err(Error::from)?
+                .into_iter()
+                .map(|object| {
+                    EmbeddedObject::new(
+                        object.prog_id().map(str::to_string),
+                        object.data().to_vec(),
+                        object.preview().map(<[u8]>::to_vec),
+                    )
+                })
+                .collect()),
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the pictures in the document body, in reading order.
+    ///
+    /// Each picture reports the paragraph it is anchored to, its alt text,
+    /// its displayed size and a reference to its data: the relationship id
+    /// for .docx (inline and floating `w:drawing` pictures) and the BLIP
+    /// store index for .doc. Pictures in headers, footers and notes are not
+    /// included; other formats return no pictures.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// for image in doc.images()? {
+    ///     println!("{:?} in paragraph {}", image.alt_text(), image.paragraph_index());
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn images(&self) -> Result<Vec<ImageRef>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                let mut images = Vec::new();
+                for (index, para) in doc.paragraphs().map_err(Error::from)?.iter().enumerate() {
+                    for run in para.runs().map_err(Error::from)? {
+                        let Some(image) = run.image() else {
+                            continue;
+                        };
+                        // Pictures whose data cannot be located are skipped
+                        let Ok(info) = doc.image_info(image) else {
+                            continue;
+                        };
+                        images.push(ImageRef {
+                            paragraph_index: index,
+                            description: info.description,
+                            name: info.name,
+                            width_emu: info.width_emu,
+                            height_emu: info.height_emu,
+                            blip_index: info.blip_index,
+                            extension: info.extension,
+                            pic_offset: Some(image.pic_offset()),
+                            ..Default::default()
+                        });
+                    }
+                }
+                Ok(images)
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => {
+                let opc = doc.opc_package();
+                let main_part = opc.main_document_part().map_err(Error::from)?;
+                let rels = main_part.rels();
+                let non_empty = |s: &str| (!s.is_empty()).then(|| s.to_string());
+
+                let mut images = Vec::new();
+                for (index, para) in doc.paragraphs().map_err(Error::from)?.iter().enumerate() {
+                    for image in para.images().map_err(Error::from)? {
+                        let extension = image
+                            .format(opc, rels)
+                            .ok()
+                            .map(|format| format.extension());
+                        images.push(ImageRef {
+                            paragraph_index: index,
+                            description: non_empty(image.description()),
+                            title: non_empty(image.title()),
+                            name: non_empty(image.name()),
+                            width_emu: image.width_emu().max(0) as u64,
+                            height_emu: image.height_emu().max(0) as u64,
+                            relationship_id: Some(image.r_embed().to_string()),
+                            extension,
+                            ..Default::default()
+                        });
+                    }
+                }
+                Ok(images)
+            },
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the picture bytes of an image, in the stored format.
+    ///
+    /// The data has the format given by [`ImageRef::extension`]; .doc
+    /// metafiles are returned decompressed. Reading pictures of .doc files
+    /// requires the `imgconv` feature, which decodes their BLIP records.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// for (i, image) in doc.images()?.iter().enumerate() {
+    ///     let name = format!("image{}.{}", i, image.extension().unwrap_or("bin"));
+    ///     std::fs::write(name, doc.image_data(image)?)?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn image_data(&self, image: &ImageRef) -> Result<Vec<u8>> {
+        match &self.inner {
+            #[cfg(all(feature = "ole", feature = "imgconv"))]
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                let offset = image.pic_offset.ok_or_else(|| {
+                    Error::InvalidReference("image has no picture offset".to_string())
+                })?;
+                let extracted = doc
+                    .image_data(&ole::doc::Image::new(offset))
+                    .map_err(|e| Error::ParseError(format!("Failed to read picture: {}", e)))?;
+                Ok(extracted.blip.get_decompressed_data()?.into_owned())
+            },
+            #[cfg(all(feature = "ole", not(feature = "imgconv")))]
+            DocumentImpl::Doc(..) => Err(Error::FeatureDisabled("imgconv".to_string())),
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => {
+                let r_id = image.relationship_id().ok_or_else(|| {
+                    Error::InvalidReference("image has no relationship id".to_string())
+                })?;
+                let opc = doc.opc_package();
+                let main_part = opc.main_document_part().map_err(Error::from)?;
+                let rel = main_part.rels().get(r_id).ok_or_else(|| {
+                    Error::InvalidReference(format!("image relationship {} not found", r_id))
+                })?;
+                let partname = rel.target_partname().map_err(Error::from)?;
+                let part = opc.get_part(&partname).map_err(Error::from)?;
+                Ok(part.try_blob()?.to_vec())
+            },
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::UnsupportedFeature(
+                "pictures are only available for .doc and .docx documents",
+            )),
+        }
+    }
+
+    /// Get the text of text boxes and shapes in the document body.
+    ///
+    /// Each entry reports the paragraph its shape is anchored to, so that the
+    /// text can be placed next to it. Covers DrawingML shapes (`wps:txbx`) and
+    /// VML text boxes (`v:textbox`) for .docx, and Escher text boxes whose
+    /// text is kept in the text box subdocument for .doc. This text is not
+    /// part of [`Document::text`]; shapes in headers and footers are not
+    /// included, and other formats return no floating text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// for shape in doc.floating_text()? {
+    ///     println!("paragraph {}: {}", shape.paragraph_index(), shape.text()?);
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn floating_text(&self) -> Result<Vec<FloatingText>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                Ok(doc
+                    .text_boxes()
+                    .map_err(Error::from)?
+                    .into_iter()
+                    .map(|text_box| {
+                        FloatingText::new(
+                            doc.paragraph_index_at(text_box.anchor_position),
+                            None,
+                            text_box
+                                .paragraphs
+                                .into_iter()
+                                .map(Paragraph::Doc)
+                                .collect(),
+                        )
+                    })
+                    .collect())
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => {
+                let mut shapes = Vec::new();
+                for (index, para) in doc.paragraphs().map_err(Error::from)?.iter().enumerate() {
+                    for text_box in para.text_boxes().map_err(Error::from)? {
+                        let name =
+                            (!text_box.name().is_empty()).then(|| text_box.name().to_string());
+                        let paragraphs = text_box
+                            .paragraphs()
+                            .iter()
+                            .cloned()
+                            .map(Paragraph::Docx)
+                            .collect();
+                        shapes.push(FloatingText::new(index, name, paragraphs));
+                    }
+                }
+                Ok(shapes)
+            },
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the SmartArt diagrams in the document body, in document order.
+    ///
+    /// Each diagram holds its node tree; see [`SmartArt::nodes`]. Their text
+    /// is part of [`Document::text`], one node per line. Only available for
+    /// .docx documents; other formats return no diagrams.
+    ///
+    /// [`SmartArt::nodes`]: crate::ooxml::pptx::SmartArt::nodes
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// for smartart in doc.smartart()? {
+    ///     println!("{}", smartart.text());
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    #[cfg(feature = "ooxml")]
+    pub fn smartart(&self) -> Result<Vec<crate::ooxml::pptx::SmartArt>> {
+        match &self.inner {
+            DocumentImpl::Docx(doc, _) => doc.smartart().map_err(Error::from),
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the SmartArt diagrams in the document body with the index of the
+    /// paragraph each one is anchored to.
+    #[cfg(feature = "ooxml")]
+    pub(crate) fn smartart_by_paragraph(
+        &self,
+    ) -> Result<Vec<(usize, crate::ooxml::pptx::SmartArt)>> {
+        let doc = match &self.inner {
+            DocumentImpl::Docx(doc, _) => doc,
+            #[allow(unreachable_patterns)]
+            _ => return Ok(Vec::new()),
+        };
+        if !doc.may_have_smartart() {
+            return Ok(Vec::new());
+        }
+        let mut diagrams = Vec::new();
+        for (index, para) in doc.paragraphs().map_err(Error::from)?.iter().enumerate() {
+            for rel_id in para.diagram_rel_ids().map_err(Error::from)? {
+                if let Some(smartart) = doc.read_smartart(&rel_id).map_err(Error::from)? {
+                    diagrams.push((index, smartart));
+                }
+            }
+        }
+        Ok(diagrams)
+    }
+
+    /// Get the heading outline of the document.
+    ///
+    /// Returns the headings in reading order with their level (1-9), text and
+    /// paragraph index. Headings are resolved from outline levels rather than
+    /// style names: `w:outlineLvl` on the paragraph or its style chain for
+    /// .docx, the paragraph's PAP and its style sheet entry for .doc, and
+    /// `text:h` elements for .odt. Other formats return an empty outline.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// for entry in doc.outline()? {
+    ///     println!("H{} {}", entry.level(), entry.text());
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn outline(&self) -> Result<Vec<OutlineEntry>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                let levels = doc.outline_levels().map_err(Error::from)?;
+                if levels.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                let paragraphs = doc.paragraphs().map_err(Error::from)?;
+                let mut outline = Vec::with_capacity(levels.len());
+                for (index, level) in levels {
+                    let Some(para) = paragraphs.get(index) else {
+                        continue;
+                    };
+                    // .doc paragraphs keep their text in the runs
+                    let text: String = para
+                        .runs()
+                        .map_err(Error::from)?
+                        .iter()
+                        .map(|run| run.text().unwrap_or_default())
+                        .collect();
+                    outline.push(OutlineEntry::new(
+                        level + 1,
+                        text.trim_matches(|c: char| c.is_whitespace() || c.is_control())
+                            .to_string(),
+                        index,
+                    ));
+                }
+                Ok(outline)
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => {
+                // Documents without a styles part can still set levels directly
+                let mut styles = doc.styles().ok();
+                let default_style = match styles.as_mut() {
+                    Some(styles) => styles
+                        .get_default(ooxml::docx::WdStyleType::Paragraph)
+                        .map_err(Error::from)?
+                        .map(|style| style.style_id().to_string()),
+                    None => None,
+                };
+
+                let mut outline = Vec::new();
+                for (index, para) in doc.paragraphs().map_err(Error::from)?.iter().enumerate() {
+                    let level = match para.outline_level().map_err(Error::from)? {
+                        Some(level) => Some(level),
+                        None => {
+                            let style_id = para.style_id().map_err(Error::from)?;
+                            match (styles.as_mut(), style_id.or_else(|| default_style.clone())) {
+                                (Some(styles), Some(id)) => {
+                                    styles.outline_level(&id).map_err(Error::from)?
+                                },
+                                _ => None,
+                            }
+                        },
+                    };
+                    // Level 9 marks body text
+                    if let Some(level) = level.filter(|&level| level < 9) {
+                        let text = para.text().map_err(Error::from)?;
+                        outline.push(OutlineEntry::new(level + 1, text.trim().to_string(), index));
+                    }
+                }
+                Ok(outline)
+            },
+            #[cfg(feature = "odf")]
+            DocumentImpl::Odt(doc) => {
+                let paragraphs = doc.paragraphs()?;
+
+                let mut outline = Vec::new();
+                for (index, para) in paragraphs.iter().enumerate() {
+                    if let Some(level) = para.outline_level() {
+                        let text = para.text()?;
+                        outline.push(OutlineEntry::new(
+                            level.clamp(1, 9),
+                            text.trim().to_string(),
+                            index,
+                        ));
+                    }
+                }
+                Ok(outline)
+            },
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the page setup of each section of the document.
+    ///
+    /// Returns the page size, orientation and margins of every section in
+    /// document order: the `w:sectPr` elements of a .docx, and the section
+    /// properties (SEP) of a .doc. Values are taken from the file only; a
+    /// size or margin the document leaves out is `None`. Other formats
+    /// return an empty list.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.doc")?;
+    /// for section in doc.sections()? {
+    ///     println!("{:?}, left margin {:?}", section.orientation(), section.margins().left);
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn sections(&self) -> Result<Vec<Section>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                let twips =
+                    |value: Option<u32>| value.map(|v| crate::common::Length::from_twips(v.into()));
+                Ok(doc
+                    .sections()
+                    .map_err(Error::from)?
+                    .into_iter()
+                    .map(|sep| {
+                        let orientation = match sep.landscape {
+                            Some(true) => PageOrientation::Landscape,
+                            _ => PageOrientation::Portrait,
+                        };
+                        let margins = PageMargins {
+                            top: twips(sep.top_margin),
+                            right: twips(sep.right_margin),
+                            bottom: twips(sep.bottom_margin),
+                            left: twips(sep.left_margin),
+                            header: twips(sep.header_distance),
+                            footer: twips(sep.footer_distance),
+                            gutter: twips(sep.gutter),
+                        };
+                        Section::new(
+                            twips(sep.page_width),
+                            twips(sep.page_height),
+                            orientation,
+                            margins,
+                        )
+                    })
+                    .collect())
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => {
+                let mut sections = doc.sections().map_err(Error::from)?;
+                Ok(sections
+                    .iter_mut()
+                    .map(|section| {
+                        let orientation = match section.orientation() {
+                            ooxml::docx::WdOrientation::Landscape => PageOrientation::Landscape,
+                            ooxml::docx::WdOrientation::Portrait => PageOrientation::Portrait,
+                        };
+                        let margins = PageMargins {
+                            top: section.top_margin().map(Into::into),
+                            right: section.right_margin().map(Into::into),
+                            bottom: section.bottom_margin().map(Into::into),
+                            left: section.left_margin().map(Into::into),
+                            header: section.header_distance().map(Into::into),
+                            footer: section.footer_distance().map(Into::into),
+                            gutter: section.gutter().map(Into::into),
+                        };
+                        Section::new(
+                            section.page_width().map(Into::into),
+                            section.page_height().map(Into::into),
+                            orientation,
+                            margins,
+                        )
+                    })
+                    .collect())
+            },
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get all tracked changes (revisions) in the document.
+    ///
+    /// Supported for .docx, .doc and .odt documents; other formats return an
+    /// empty list. Legacy .doc files do not expose authors or dates, so
+    /// [`Revision::author`] and [`Revision::date`] are `None` there.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// for revision in doc.revisions()? {
+    ///     println!("{} by {:?}: {}", revision.revision_type(), revision.author(), revision.text());
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn revisions(&self) -> Result<Vec<Revision>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                // Merge neighbouring runs carrying the same revision mark
+                let mut revisions: Vec<Revision> = Vec::new();
+                let mut previous = None;
+                for para in doc.paragraphs().map_err(Error::from)? {
+                    for run in para.runs().map_err(Error::from)? {
+                        let revision_type = if run.is_deleted() {
+                            RevisionType::Delete
+                        } else if run.is_inserted() {
+                            RevisionType::Insert
+                        } else {
+                            previous = None;
+                            continue;
+                        };
+                        let text = run.text().map_err(Error::from)?;
+                        match revisions.last_mut() {
+                            Some(last) if previous == Some(revision_type) => last.push_text(text),
+                            _ => revisions.push(Revision::new(
+                                revision_type,
+                                None,
+                                None,
+                                text.to_string(),
+                            )),
+                        }
+                        previous = Some(revision_type);
+                    }
+                }
+                Ok(revisions)
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => {
+                use ooxml::docx::RevisionType as DocxRevisionType;
+
+                Ok(doc
+                    .revisions()
+                    .map_err(Error::from)?
+                    .into_iter()
+                    .map(|revision| {
+                        let revision_type = match revision.revision_type() {
+                            DocxRevisionType::Insert | DocxRevisionType::TableInsert => {
+                                RevisionType::Insert
+                            },
+                            DocxRevisionType::Delete | DocxRevisionType::TableDelete => {
+                                RevisionType::Delete
+                            },
+                            DocxRevisionType::MoveFrom => RevisionType::MoveFrom,
+                            DocxRevisionType::MoveTo => RevisionType::MoveTo,
+                            DocxRevisionType::FormatChange => RevisionType::FormatChange,
+                            DocxRevisionType::Unknown => RevisionType::Other,
+                        };
+                        Revision::new(
+                            revision_type,
+                            Some(revision.author().to_string()).filter(|a| !a.is_empty()),
+                            revision.date().map(str::to_string),
+                            revision.text().to_string(),
+                        )
+                    })
+                    .collect())
+            },
+            #[cfg(feature = "odf")]
+            DocumentImpl::Odt(doc) => {
+                use crate::odf::ChangeType;
+
+                Ok(doc
+                    .track_changes()?
+                    .into_iter()
+                    .map(|change| {
+                        let revision_type = match change.change_type {
+                            ChangeType::Insertion => RevisionType::Insert,
+                            ChangeType::Deletion => RevisionType::Delete,
+                            ChangeType::FormatChange => RevisionType::FormatChange,
+                        };
+                        Revision::new(revision_type, change.author, change.date, change.content)
+                    })
+                    .collect())
+            },
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get all text content with tracked changes materialized.
+    ///
+    /// Paragraphs are separated by newlines. Every mode is supported for .docx
+    /// and .doc documents. Other formats keep no deleted text in the body, so
+    /// only [`RevisionMode::AcceptAll`] is supported there and returns the same
+    /// text as [`Document::text`]; the other modes return
+    /// [`Error::UnsupportedFeature`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    /// use litchi::document::RevisionMode;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// let accepted = doc.text_with_revisions(RevisionMode::AcceptAll)?;
+    /// let original = doc.text_with_revisions(RevisionMode::RejectAll)?;
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn text_with_revisions(&self, mode: RevisionMode) -> Result<String> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => {
+                let doc = doc.document()?;
+                let mut paragraphs = Vec::new();
+                for para in doc.paragraphs().map_err(Error::from)? {
+                    let mut text = String::new();
+                    let mut open_markup = None;
+                    for run in para.runs().map_err(Error::from)? {
+                        let markup = if run.is_deleted() {
+                            Some("--")
+                        } else if run.is_inserted() {
+                            Some("++")
+                        } else {
+                            None
+                        };
+                        match (mode, markup) {
+                            (RevisionMode::AcceptAll, Some("--"))
+                            | (RevisionMode::RejectAll, Some("++")) => continue,
+                            (RevisionMode::ShowMarkup, _) if markup != open_markup => {
+                                if let Some(close) = open_markup {
+                                    text.push_str(close);
+                                    text.push('}');
+                                }
+                                if let Some(open) = markup {
+                                    text.push('{');
+                                    text.push_str(open);
+                                }
+                                open_markup = markup;
+                            },
+                            _ => {},
+                        }
+                        text.push_str(run.text().map_err(Error::from)?);
+                    }
+                    if let Some(close) = open_markup {
+                        text.push_str(close);
+                        text.push('}');
+                    }
+                    paragraphs.push(text);
+                }
+                Ok(paragraphs.join("\n"))
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => doc.text_with_revisions(mode).map_err(Error::from),
+            #[allow(unreachable_patterns)]
+            _ if mode == RevisionMode::AcceptAll => self.text(),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::UnsupportedFeature(
+                "only RevisionMode::AcceptAll is supported for this document format",
+            )),
+        }
+    }
+
+    /// Check whether the document carries a VBA project (macros).
+    ///
+    /// This is true for .doc files with a `Macros` storage and for
+    /// macro-enabled .docm files with a `vbaProject.bin` part. Other formats
+    /// have no VBA macros.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a .doc file cannot be parsed, since its macros
+    /// are only found by parsing the document.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docm")?;
+    /// if doc.has_macros()? {
+    ///     println!("document contains macros");
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn has_macros(&self) -> Result<bool> {
+        Ok(match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => doc.document()?.vba_project_data().is_some(),
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => doc.opc_package().vba_project_part().is_some(),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        })
+    }
+
+    /// Get the VBA project of the document, with the source code of its
+    /// modules.
+    ///
+    /// Returns `None` when the document has no macros (see
+    /// [`Self::has_macros`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project is present but cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docm")?;
+    /// if let Some(project) = doc.vba_project()? {
+    ///     for module in project.modules() {
+    ///         println!("' {}\n{}", module.name(), module.source());
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    #[cfg(feature = "ole")]
+    pub fn vba_project(&self) -> Result<Option<ole::vba::VbaProject>> {
+        let data = match &self.inner {
+            DocumentImpl::Doc(doc, _) => doc.document()?.vba_project_data(),
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => doc
+                .opc_package()
+                .vba_project_part()
+                .map(|p| p.try_blob())
+                .transpose()?,
+            #[allow(unreachable_patterns)]
+            _ => None,
+        };
+        data.map(|data| ole::vba::VbaProject::parse(data).map_err(Error::from))
+            .transpose()
+    }
+
+    /// Get the document default languages as BCP-47 tags, with separate
+    /// slots for Latin, East Asian and complex script text.
+    ///
+    /// These apply to text that declares no language of its own and come
+    /// from the document defaults and default paragraph style (.docx), the
+    /// "Normal" style (.doc), the default paragraph style (.odt) or
+    /// `\deflang` and `\deflangfe` (.rtf). .pages documents have no
+    /// default languages. See [`Run::language`](super::Run::language) for
+    /// the languages of individual runs.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// let language = doc.default_language()?;
+    /// println!("Latin: {:?}, East Asian: {:?}", language.latin, language.east_asian);
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn default_language(&self) -> Result<crate::common::Language> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, _) => Ok(doc.document()?.default_language()),
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, _) => doc.default_language().map_err(Error::from),
+            #[cfg(feature = "iwa")]
+            DocumentImpl::Pages(_) => Ok(crate::common::Language::default()),
+            #[cfg(feature = "rtf")]
+            DocumentImpl::Rtf(doc) => Ok(doc.default_language().clone()),
+            #[cfg(feature = "odf")]
+            DocumentImpl::Odt(doc) => Ok(doc.default_language()),
+        }
+    }
+
+    /// Get the editing protection of the document.
+    ///
+    /// Reports the enforced editing restriction (.docx, .doc, .rtf), the
+    /// read-only recommendation and password to modify (.docx, .doc,
+    /// `LoadReadonly` in .odt), and the "final" mark and `DocSecurity` flags
+    /// of the document properties (.docx, .doc). .pages documents are never
+    /// protected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    /// use litchi::common::ProtectionKind;
+    ///
+    /// let doc = Document::open("contract.docx")?;
+    /// let protection = doc.protection()?;
+    /// if protection.kind >= ProtectionKind::Final {
+    ///     println!("{:?}, password: {}", protection.kind, protection.has_password);
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn protection(&self) -> Result<Protection> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(doc, metadata) => {
+                let doc = doc.document()?;
+                Ok(doc.protection().or(Protection::from_metadata(metadata)))
+            },
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(doc, metadata) => {
+                use crate::common::ProtectionKind;
+                use ooxml::docx::ProtectionType;
+
+                let mut protection = Protection::from_metadata(metadata);
+                let Some(settings) = doc.settings().map_err(Error::from)? else {
+                    return Ok(protection);
+                };
+                if settings.is_protected()
+                    && let Some(protection_type) = settings.protection_type()
+                {
+                    protection = protection.or(Protection {
+                        kind: match protection_type {
+                            ProtectionType::ReadOnly => ProtectionKind::ReadOnly,
+                            ProtectionType::Comments => ProtectionKind::CommentsOnly,
+                            ProtectionType::TrackedChanges => ProtectionKind::TrackedChanges,
+                            ProtectionType::Forms => ProtectionKind::Forms,
+                        },
+                        has_password: settings.has_protection_password(),
+                    });
+                }
+                if settings.is_read_only_recommended() {
+                    protection =
+                        protection.or(Protection::new(ProtectionKind::ReadOnlyRecommended));
+                }
+                if settings.has_write_password() {
+                    protection = protection.or(Protection::with_password(ProtectionKind::ReadOnly));
+                }
+                Ok(protection)
+            },
+            #[cfg(feature = "iwa")]
+            DocumentImpl::Pages(_) => Ok(Protection::default()),
+            #[cfg(feature = "rtf")]
+            DocumentImpl::Rtf(doc) => {
+                use crate::common::ProtectionKind;
+                use crate::rtf::ProtectionType;
+
+                let protection = doc.protection();
+                if !protection.is_protected() {
+                    return Ok(Protection::default());
+                }
+                Ok(Protection {
+                    kind: match protection.protection_type {
+                        ProtectionType::None => ProtectionKind::None,
+                        ProtectionType::ReadOnly => ProtectionKind::ReadOnly,
+                        ProtectionType::RevisionTracking => ProtectionKind::TrackedChanges,
+                        ProtectionType::Comments => ProtectionKind::CommentsOnly,
+                        ProtectionType::Forms => ProtectionKind::Forms,
+                    },
+                    has_password: protection.has_password,
+                })
+            },
+            #[cfg(feature = "odf")]
+            DocumentImpl::Odt(doc) => doc.protection(),
+        }
+    }
+
+    /// Get the digital signatures of the document.
+    ///
+    /// Each signature reports its signer certificate, signing time and the
+    /// parts it covers, and whether those parts still match the signed
+    /// digests. Only .docx packages carry such signatures; other formats
+    /// return an empty list. See [`crate::ooxml::signatures`] for what is
+    /// and is not verified.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("contract.docx")?;
+    /// for signature in doc.signatures() {
+    ///     println!("{:?} at {:?}", signature.subject, signature.signing_time);
+    ///     if !signature.digests_valid {
+    ///         println!("modified after signing: {:?}", signature.invalid_parts);
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    #[cfg(feature = "signatures")]
+    pub fn signatures(&self) -> Vec<ooxml::signatures::SignatureInfo> {
+        match &self.inner {
+            DocumentImpl::Docx(doc, _) => ooxml::signatures::read_signatures(doc.opc_package()),
+            #[allow(unreachable_patterns)]
+            _ => Vec::new(),
+        }
+    }
+
+    /// Get document metadata.
+    ///
+    /// Extracts metadata from the document such as title, author, creation date, etc.
+    /// For OLE (.doc) files, this reads from SummaryInformation and DocumentSummaryInformation streams,
+    /// including user-defined properties.
+    /// For OOXML (.docx) files, this reads from the core, extended (app) and custom properties parts.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.doc")?;
+    /// let metadata = doc.metadata()?;
+    /// if let Some(title) = &metadata.title {
+    ///     println!("Title: {}", title);
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn metadata(&self) -> Result<crate::common::Metadata> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            DocumentImpl::Doc(_, metadata) => Ok(metadata.clone()),
+            #[cfg(feature = "ooxml")]
+            DocumentImpl::Docx(_, metadata) => Ok(metadata.clone()),
+            #[cfg(feature = "iwa")]
+            DocumentImpl::Pages(doc) => {
+                // Extract metadata from Pages bundle metadata
+                let bundle_metadata = doc.bundle().metadata();
+                let mut metadata = crate::common::Metadata::default();
+
+                // Extract title from properties
+                if let Some(title) = bundle_metadata.get_property_string("Title") {
+                    metadata.title = Some(title);
+                }
+
+                // Extract author from properties
+                if let Some(author) = bundle_metadata.get_property_string("Author") {
+                    metadata.author = Some(author);
+                }
+
+                // Extract document identifier
+                if let Some(doc_id) = bundle_metadata.document_identifier() {
+                    metadata.description = Some(format!("Document ID: {}", doc_id));
+                }
+
+                Ok(metadata)
+            },
+            #[cfg(feature = "rtf")]
+            DocumentImpl::Rtf(_doc) => {
+                // RTF doesn't have standard metadata in the same way
+                // Metadata would need to be parsed from \info group
+                Ok(crate::common::Metadata::default())
+            },
+            #[cfg(feature = "odf")]
+            DocumentImpl::Odt(doc) => doc.metadata(),
+        }
+    }
+}
+
+/// Keep the results of Word binary fields (`0x13` code `0x14` result
+/// `0x15`) and drop their codes, which are not part of the visible text.
+/// Page breaks (`0x0C`) become line breaks, as in .docx text.
+#[cfg(any(feature = "ole", feature = "iwa", feature = "rtf", feature = "odf"))]
+fn field_results(text: &str) -> std::borrow::Cow<'_, str> {
+    if !text.contains(['\u{c}', '\u{13}', '\u{14}', '\u{15}']) {
+        return text.into();
+    }
+    let mut result = String::with_capacity(text.len());
+    // For each open field, whether its code is still being read
+    let mut in_code = Vec::new();
+    for ch in text.chars() {
+        match ch {
+            '\u{13}' => in_code.push(true),
+            '\u{14}' => {
+                if let Some(code) = in_code.last_mut() {
+                    *code = false;
+                }
+            },
+            '\u{15}' => {
+                in_code.pop();
+            },
+            _ if in_code.contains(&true) => {},
+            '\u{c}' => result.push('\n'),
+            ch => result.push(ch),
+        }
+    }
+    result.into()
+}
+
+/// Walk the body of a .doc file, reading it from the package a few
+/// paragraphs at a time so that nothing after the point where the visitor
+/// stops is read.
+#[cfg(feature = "ole")]
+fn walk_doc<R: std::io::Read + std::io::Seek>(
+    doc: &DocFile<R>,
+    visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+) -> Result<ControlFlow<()>> {
+    let reader = ole::doc::reader::BodyReader::open(doc.package().ole_file())?;
+    let Some(mut reader) = reader else {
+        return walk_elements(&doc.document()?.body_elements()?, visitor);
+    };
+    loop {
+        // The package is only locked while reading, so the visitor may use the document
+        let elements = reader.next_elements(doc.package().ole_file())?;
+        let Some(elements) = elements else {
+            return Ok(ControlFlow::Continue(()));
+        };
+        if walk_elements(&elements, visitor)?.is_break() {
+            return Ok(ControlFlow::Break(()));
+        }
+    }
+}
+
+/// Report paragraphs and tables that have already been parsed, as
+/// [`Document::walk_text`] does for formats that are read when opened.
+#[cfg(any(feature = "ole", feature = "iwa", feature = "rtf", feature = "odf"))]
+fn walk_elements(
+    elements: &[super::DocumentElement],
+    visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+) -> Result<ControlFlow<()>> {
+    let options = TextOptions::default();
+    for element in elements {
+        match element {
+            super::DocumentElement::Paragraph(para) => {
+                // Join run texts, since .doc paragraphs keep their text in the
+                // runs; paragraphs without runs have plain text
+                let runs = para.runs()?;
+                let text: String = if runs.is_empty() {
+                    para.text()?
+                } else {
+                    runs.iter().map(|run| run.text()).collect::<Result<_>>()?
+                };
+                let text = options.paragraph_text(&field_results(&text));
+                visit!(visitor, TextEvent::ParagraphStart);
+                if !text.is_empty() {
+                    visit!(visitor, TextEvent::Text(&text));
+                }
+            },
+            super::DocumentElement::Table(table) => {
+                visit!(visitor, TextEvent::TableStart);
+                for row in table.rows()? {
+                    visit!(visitor, TextEvent::TableRowStart);
+                    for cell in row.cells()? {
+                        visit!(visitor, TextEvent::TableCellStart);
+                        // Some formats only expose the text of a cell
+                        let elements = cell.elements()?;
+                        let flow = if elements.is_empty() {
+                            visit_lines(&cell.text()?, visitor)
+                        } else {
+                            walk_elements(&elements, visitor)?
+                        };
+                        if flow.is_break() {
+                            return Ok(ControlFlow::Break(()));
+                        }
+                    }
+                }
+                visit!(visitor, TextEvent::TableEnd);
+            },
+        }
+    }
+    Ok(ControlFlow::Continue(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Length;
+    use std::path::PathBuf;
+
+    fn test_data_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data")
+    }
+
+    /// Build a .docx from a real package with its `word/document.xml` replaced.
+    #[cfg(feature = "ooxml")]
+    fn docx_with_body(body: &str) -> Vec<u8> {
+        use std::io::{Cursor, Read, Write};
+
+        let source = std::fs::read(test_data_path().join("ooxml/docx/footnotes.docx")).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(source)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            let name = entry.name().to_string();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            writer
+                .start_file(name.as_str(), zip::write::SimpleFileOptions::default())
+                .unwrap();
+            if name == "word/document.xml" {
+                writer.write_all(body.as_bytes()).unwrap();
+            } else {
+                writer.write_all(&data).unwrap();
+            }
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_open_docx() {
+        let path = test_data_path().join("ooxml/docx/FancyFoot.docx");
+        let doc = Document::open(&path);
+        assert!(doc.is_ok(), "Failed to open DOCX file: {:?}", doc.err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_open_doc() {
+        let path = test_data_path().join("ole/doc/FancyFoot.doc");
+        let doc = Document::open(&path);
+        assert!(doc.is_ok(), "Failed to open DOC file: {:?}", doc.err());
+    }
+
+    #[test]
+    #[cfg(feature = "rtf")]
     fn test_document_open_rtf() {
         let path = test_data_path().join("rtf/testUnicode.rtf");
         let doc = Document::open(&path);
@@ -802,96 +2338,582 @@ mod tests {
     }
 
     #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_elements_docx() {
-        let path = test_data_path().join("ooxml/docx/FancyFoot.docx");
-        let doc = Document::open(&path).expect("Failed to open DOCX");
-        let elements = doc.elements().expect("Failed to get elements");
-        assert!(!elements.is_empty(), "Expected at least one element");
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_elements_docx() {
+        let path = test_data_path().join("ooxml/docx/FancyFoot.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let elements = doc.elements().expect("Failed to get elements");
+        assert!(!elements.is_empty(), "Expected at least one element");
+
+        // Check element types
+        for element in elements {
+            match element {
+                super::super::DocumentElement::Paragraph(_) => {
+                    // Paragraph element
+                },
+                super::super::DocumentElement::Table(_) => {
+                    // Table element
+                },
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_metadata_docx() {
+        use chrono::{TimeZone, Utc};
+
+        let path = test_data_path().join("ooxml/docx/documentProperties.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX file");
+        let metadata = doc.metadata().expect("Failed to read metadata");
+
+        assert_eq!(metadata.title.as_deref(), Some("Hello World"));
+        assert_eq!(metadata.author.as_deref(), Some("Paolo Mottadelli"));
+        assert_eq!(metadata.revision.as_deref(), Some("5"));
+        assert_eq!(
+            metadata.created,
+            Some(Utc.with_ymd_and_hms(2009, 7, 20, 13, 12, 0).unwrap())
+        );
+        assert_eq!(
+            metadata.company.as_deref(),
+            Some("The Apache Software Foundation")
+        );
+        assert_eq!(metadata.template.as_deref(), Some("Normal.dotm"));
+        assert_eq!(metadata.word_count, Some(2));
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_metadata_doc() {
+        use chrono::{TimeZone, Utc};
+
+        let path = test_data_path().join("ole/doc/documentProperties.doc");
+        let doc = Document::open(&path).expect("Failed to open DOC");
+        let metadata = doc.metadata().expect("Failed to get metadata");
+        assert_eq!(metadata.title.as_deref(), Some("This is document title"));
+        assert_eq!(metadata.author.as_deref(), Some("Sergey Vladimirov"));
+        assert_eq!(
+            metadata.created,
+            Some(Utc.with_ymd_and_hms(2011, 7, 6, 8, 2, 37).unwrap())
+        );
+        assert_eq!(
+            metadata.custom_properties.get("This is user field title"),
+            Some(&crate::common::CustomPropertyValue::String(
+                "This is user field value".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_open_nonexistent_file() {
+        let path = test_data_path().join("nonexistent_file.docx");
+        let result = Document::open(&path);
+        assert!(result.is_err(), "Expected error for nonexistent file");
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_from_bytes_invalid_data() {
+        let bytes = b"This is not a valid document file".to_vec();
+        let result = Document::from_bytes(bytes);
+        assert!(result.is_err(), "Expected error for invalid data");
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_complex_lists_docx() {
+        let path = test_data_path().join("ooxml/docx/ComplexNumberedLists.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let text = doc.text().expect("Failed to extract text");
+        assert!(!text.is_empty(), "Expected non-empty text");
+
+        let paragraphs = doc.paragraphs().expect("Failed to get paragraphs");
+        assert!(
+            !paragraphs.is_empty(),
+            "Expected paragraphs in list document"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_footnotes_docx() {
+        let path = test_data_path().join("ooxml/docx/footnotes.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let text = doc.text().expect("Failed to extract text");
+        assert!(!text.is_empty(), "Expected non-empty text");
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_endnotes_docx() {
+        let path = test_data_path().join("ooxml/docx/endnotes.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let text = doc.text().expect("Failed to extract text");
+        assert!(!text.is_empty(), "Expected non-empty text");
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_notes_docx() {
+        use crate::markdown::{MarkdownOptions, ToMarkdown};
+
+        let doc = Document::open(test_data_path().join("ooxml/docx/footnotes.docx")).unwrap();
+        let footnotes = doc.footnotes().unwrap();
+        assert_eq!(footnotes.len(), 1);
+        assert_eq!(footnotes[0].kind(), NoteKind::Footnote);
+        assert_eq!(footnotes[0].id(), 1);
+        assert_eq!(footnotes[0].paragraph_index(), Some(0));
+        assert_eq!(footnotes[0].text().unwrap(), "snoska");
+        assert!(doc.endnotes().unwrap().is_empty());
+
+        let markdown = doc
+            .to_markdown_with_options(&MarkdownOptions::new().with_footnotes(true))
+            .unwrap();
+        assert!(markdown.starts_with("Eto ochen prostoy text so snoskoy[^1]\n"));
+        assert!(markdown.ends_with("\n[^1]: snoska\n"));
+        assert!(!doc.to_markdown().unwrap().contains("[^1]"));
+
+        let doc = Document::open(test_data_path().join("ooxml/docx/endnotes.docx")).unwrap();
+        let endnotes = doc.endnotes().unwrap();
+        assert_eq!(endnotes.len(), 1);
+        assert_eq!(endnotes[0].id(), 2);
+        assert_eq!(endnotes[0].text().unwrap(), "XXX");
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_images_docx() {
+        let doc = Document::open(test_data_path().join("ooxml/docx/drawing.docx")).unwrap();
+        let images = doc.images().unwrap();
+        assert_eq!(images.len(), 15);
+
+        let image = &images[1];
+        assert_eq!(image.paragraph_index(), 83);
+        assert_eq!(image.alt_text(), Some("ntop1"));
+        assert_eq!(image.title(), None);
+        assert_eq!(image.name(), Some("Рисунок 6"));
+        assert_eq!((image.width_emu(), image.height_emu()), (2362200, 276225));
+        assert_eq!(image.relationship_id(), Some("rId8"));
+        assert_eq!(image.blip_index(), None);
+        assert_eq!(image.extension(), Some("png"));
+
+        assert!(doc.image_data(image).unwrap().starts_with(b"\x89PNG"));
+
+        // The first picture has no alt text; pictures in table cells count too
+        assert_eq!(images[0].alt_text(), None);
+        assert_eq!(images[10].extension(), Some("gif"));
+        assert!(
+            images
+                .windows(2)
+                .all(|w| w[0].paragraph_index() <= w[1].paragraph_index())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_images_doc() {
+        let doc = Document::open(test_data_path().join("ole/doc/testPictures.doc")).unwrap();
+        let images = doc.images().unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].paragraph_index(), 7);
+        assert_eq!(images[0].width_emu(), 2723515);
+        assert_eq!(images[0].height_emu(), 2343150);
+        assert_eq!(images[0].blip_index(), Some(6));
+        assert_eq!(images[0].relationship_id(), None);
+        assert_eq!(images[0].extension(), Some("png"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_image_placeholders_markdown() {
+        use crate::markdown::{ImageHandling, MarkdownOptions, ToMarkdown};
+
+        let doc = Document::open(test_data_path().join("ooxml/docx/drawing.docx")).unwrap();
+        assert!(!doc.to_markdown().unwrap().contains("]("));
+
+        let options = MarkdownOptions::new().with_images(ImageHandling::Placeholder);
+        for parallel in [true, false] {
+            let markdown = doc
+                .to_markdown_with_options(&options.clone().with_parallel(parallel))
+                .unwrap();
+            assert!(markdown.contains("\n![ntop1](image_2.png)\n"));
+            assert!(markdown.contains("![cbonds_logo_eng](image_3.png) ![pixel](image_4.png)"));
+            assert!(markdown.contains("![RFCM](image_11.gif)"));
+
+            // Placeholders appear once each, in document order
+            let positions: Vec<usize> = (1..=15)
+                .map(|n| {
+                    let name = format!("](image_{}.", n);
+                    assert_eq!(markdown.matches(&name).count(), 1);
+                    markdown.find(&name).unwrap()
+                })
+                .collect();
+            assert!(positions.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_image_dir_markdown() {
+        use crate::markdown::{MarkdownOptions, ToMarkdown};
+
+        let dir = tempfile::tempdir().unwrap();
+        let image_dir = dir.path().join("media");
+        let doc = Document::open(test_data_path().join("ooxml/docx/drawing.docx")).unwrap();
+        let markdown = doc
+            .to_markdown_with_options(&MarkdownOptions::new().with_image_dir(&image_dir))
+            .unwrap();
+
+        let prefix = image_dir.to_string_lossy().replace('\\', "/");
+        let links: Vec<&str> = markdown
+            .split("](")
+            .skip(1)
+            .map(|rest| &rest[..rest.find(')').unwrap()])
+            .filter(|link| link.starts_with(&prefix))
+            .collect();
+        assert_eq!(links.len(), 15);
+        assert!(links[1].ends_with("/image_002.png"));
+        assert!(markdown.contains("![RFCM]("));
+
+        // One picture is repeated seven times but stored once
+        let mut files: Vec<&str> = links.clone();
+        files.sort_unstable();
+        files.dedup();
+        assert_eq!(files.len(), 9);
+        assert_eq!(links.iter().filter(|&&link| link == links[3]).count(), 7);
+        assert_eq!(std::fs::read_dir(&image_dir).unwrap().count(), 9);
+        for file in files {
+            assert!(std::path::Path::new(file).is_file(), "missing {}", file);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_outline_docx() {
+        // Russian-localized style IDs ("1", "2", "3") carry w:outlineLvl
+        let doc = Document::open(test_data_path().join("ooxml/docx/drawing.docx")).unwrap();
+        let outline = doc.outline().unwrap();
+        assert_eq!(
+            outline[1],
+            OutlineEntry::new(1, "Ведомости и МАЗАЙ".to_string(), 13)
+        );
+        assert_eq!(outline[4].level(), 2);
+        assert_eq!(outline[4].text(), "Репортажи");
+        assert_eq!(outline[4].paragraph_index(), 40);
+        let paragraphs = doc.paragraphs().unwrap();
+        assert_eq!(paragraphs[40].text().unwrap().trim(), "Репортажи");
+
+        let doc = Document::open(test_data_path().join("ooxml/docx/FancyFoot.docx")).unwrap();
+        assert_eq!(
+            doc.outline().unwrap(),
+            vec![OutlineEntry::new(1, "HEADING TEXT".to_string(), 1)]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_outline_doc() {
+        let doc = Document::open(test_data_path().join("ole/doc/Lists.doc")).unwrap();
+        assert_eq!(
+            doc.outline().unwrap(),
+            vec![OutlineEntry::new(1, "Heading Level 1".to_string(), 0)]
+        );
+
+        let doc = Document::open(test_data_path().join("ole/doc/footnote.doc")).unwrap();
+        assert!(doc.outline().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "odf")]
+    fn test_document_outline_odt() {
+        let mut builder = crate::odf::DocumentBuilder::new();
+        builder.add_heading("Introduction", 1).unwrap();
+        builder.add_paragraph("Body text").unwrap();
+        builder.add_heading("Details", 2).unwrap();
+        let doc = Document::from_bytes(builder.build().unwrap()).unwrap();
+
+        assert_eq!(
+            doc.outline().unwrap(),
+            vec![
+                OutlineEntry::new(1, "Introduction".to_string(), 0),
+                OutlineEntry::new(2, "Details".to_string(), 2),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_list_info_docx() {
+        use crate::markdown::ToMarkdown;
+
+        let path = test_data_path().join("ooxml/docx/ComplexNumberedLists.docx");
+        let doc = Document::open(&path).unwrap();
+        let paragraphs = doc.paragraphs().unwrap();
+        let labels: Vec<_> = paragraphs[1..=15]
+            .iter()
+            .map(|p| {
+                p.list_info()
+                    .map(|l| (l.level(), l.number_text().to_string()))
+            })
+            .collect();
+        let expected = [
+            Some((0, "1.")),
+            Some((0, "2.")),
+            Some((1, "a.")),
+            Some((1, "b.")),
+            Some((1, "c.")),
+            Some((0, "3.")),
+            Some((0, "4.")),
+            // A new list restarts at 1
+            Some((0, "1.")),
+            Some((0, "2.")),
+            Some((0, "3.")),
+            // A list overriding its start value
+            Some((0, "10.")),
+            Some((0, "11.")),
+            None,
+            Some((0, "12.")),
+            Some((0, "13.")),
+        ];
+        let expected: Vec<_> = expected
+            .iter()
+            .map(|e| e.map(|(level, text)| (level, text.to_string())))
+            .collect();
+        assert_eq!(labels, expected);
+        assert!(paragraphs[1].list_info().unwrap().is_ordered());
+        assert!(paragraphs[0].list_info().is_none());
+
+        let markdown = doc.to_markdown().unwrap();
+        assert!(markdown.contains("2. Entry #2, with children\n\n  1. 2-a\n\n"));
+        assert!(markdown.contains("10. Jump to new list at 10"));
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_paragraphs_range_docx() {
+        let path = test_data_path().join("ooxml/docx/ComplexNumberedLists.docx");
+        let doc = Document::open(&path).unwrap();
+        let paragraphs = doc.paragraphs().unwrap();
+        let summary = |paras: &[Paragraph]| -> Vec<_> {
+            paras
+                .iter()
+                .map(|p| {
+                    let label = p.list_info().map(|l| l.number_text().to_string());
+                    (p.text().unwrap(), label)
+                })
+                .collect()
+        };
+
+        // List numbering continues from the paragraphs before the range
+        let range = doc.paragraphs_range(4..9).unwrap();
+        assert_eq!(summary(&range), summary(&paragraphs[4..9]));
+        assert_eq!(range[0].list_info().unwrap().number_text(), "b.");
+
+        let tail = doc.paragraphs_range(10..usize::MAX).unwrap();
+        assert_eq!(summary(&tail), summary(&paragraphs[10..]));
+        assert!(doc.paragraphs_range(100..200).unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_find_across_runs() {
+        let path = test_data_path().join("ooxml/docx/table-alignment.docx");
+        let doc = Document::open(&path).unwrap();
+
+        // "Consectetur" is split into the runs "Cons", "ectet" and "ur"
+        let options = SearchOptions::new()
+            .with_case_insensitive(true)
+            .with_whole_word(true);
+        let matches = doc.find("CONSECTETUR", &options).unwrap();
+        assert!(!matches.is_empty());
+        let m = &matches[0];
+        assert_eq!(m.location(), MatchLocation::Paragraph { index: 4 });
+        assert_eq!(m.text(), "Consectetur");
+        let segments: Vec<_> = m.segments().iter().map(|s| (s.run(), s.range())).collect();
+        assert_eq!(segments, [(0, 0..4), (1, 0..5), (2, 0..2)]);
 
-        // Check element types
-        for element in elements {
-            match element {
-                super::super::DocumentElement::Paragraph(_) => {
-                    // Paragraph element
-                },
-                super::super::DocumentElement::Table(_) => {
-                    // Table element
-                },
-            }
-        }
+        assert!(
+            doc.find("CONSECTETUR", &SearchOptions::new())
+                .unwrap()
+                .is_empty()
+        );
+        assert!(doc.find("Consect", &options).unwrap().is_empty());
     }
 
     #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_metadata_docx() {
-        let path = test_data_path().join("ooxml/docx/documentProperties.docx");
-        let doc = Document::open(&path).expect("Failed to open DOCX");
-        let metadata = doc.metadata().expect("Failed to get metadata");
-        // Document may or may not have metadata, but the call should succeed
-        let _ = metadata.title;
-        let _ = metadata.author;
+    #[cfg(feature = "ole")]
+    fn test_document_list_info_doc() {
+        use crate::markdown::ToMarkdown;
+
+        let doc = Document::open(test_data_path().join("ole/doc/Lists.doc")).unwrap();
+        let paragraphs = doc.paragraphs().unwrap();
+        assert!(paragraphs[0].list_info().is_none());
+
+        let bullet = paragraphs[2].list_info().unwrap();
+        assert!(!bullet.is_ordered());
+        assert_eq!(bullet.number_text(), "•");
+        assert_eq!(paragraphs[20].list_info().unwrap().level(), 4);
+
+        let labels: Vec<_> = paragraphs[25..=33]
+            .iter()
+            .map(|p| p.list_info().unwrap().number_text().to_string())
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "1.", "2.", "2.1.", "2.2.", "2.2.1.", "2.2.2.", "2.2.2.1.", "2.2.3.", "3."
+            ]
+        );
+        assert_eq!(paragraphs[27].list_info().unwrap().level(), 1);
+
+        let markdown = doc.to_markdown().unwrap();
+        assert!(markdown.contains("- ML 1:2\n\n  - ML 2:1\n\n"));
+        assert!(markdown.contains("2. OL 2\n\n  1. OL 2.1\n\n"));
     }
 
     #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_metadata_doc() {
-        // Note: documentProperties.doc has a metadata parsing issue causing overflow
-        // Use FancyFoot.doc instead which has working metadata
-        let path = test_data_path().join("ole/doc/FancyFoot.doc");
-        let doc = Document::open(&path).expect("Failed to open DOC");
-        let metadata = doc.metadata().expect("Failed to get metadata");
-        let _ = metadata.title;
-        let _ = metadata.author;
+    #[cfg(feature = "ooxml")]
+    fn test_document_revisions_docx() {
+        // Swap the body of a real package for one with tracked changes
+        let body = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:r><w:t xml:space="preserve">Hello </w:t></w:r><w:del w:id="1" w:author="Alice" w:date="2024-05-01T10:00:00Z"><w:r><w:delText>cruel </w:delText></w:r></w:del><w:ins w:id="2" w:author="Bob"><w:r><w:t xml:space="preserve">brave new </w:t></w:r></w:ins><w:r><w:t>world</w:t></w:r></w:p>
+</w:body></w:document>"#;
+        let doc = Document::from_bytes(docx_with_body(body)).unwrap();
+        let revisions = doc.revisions().unwrap();
+        assert_eq!(revisions.len(), 2);
+        assert_eq!(revisions[0].revision_type(), RevisionType::Delete);
+        assert_eq!(revisions[0].author(), Some("Alice"));
+        assert_eq!(revisions[0].date(), Some("2024-05-01T10:00:00Z"));
+        assert_eq!(revisions[0].text().trim(), "cruel");
+        assert_eq!(revisions[1].revision_type(), RevisionType::Insert);
+        assert_eq!(revisions[1].date(), None);
+
+        assert_eq!(
+            doc.text_with_revisions(RevisionMode::AcceptAll).unwrap(),
+            "Hello brave new world"
+        );
+        assert_eq!(
+            doc.text_with_revisions(RevisionMode::RejectAll).unwrap(),
+            "Hello cruel world"
+        );
+        assert_eq!(
+            doc.text_with_revisions(RevisionMode::ShowMarkup).unwrap(),
+            "Hello {--cruel --}{++brave new ++}world"
+        );
     }
 
     #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_open_nonexistent_file() {
-        let path = test_data_path().join("nonexistent_file.docx");
-        let result = Document::open(&path);
-        assert!(result.is_err(), "Expected error for nonexistent file");
+    #[cfg(all(feature = "ooxml", feature = "formula"))]
+    fn test_document_math_markdown_docx() {
+        use crate::markdown::{FormulaStyle, MarkdownOptions, ToMarkdown};
+
+        let body = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:m="http://schemas.openxmlformats.org/officeDocument/2006/math"><w:body>
+<w:p><w:r><w:t xml:space="preserve">Let </w:t></w:r><m:oMath><m:r><m:t>x</m:t></m:r></m:oMath><w:r><w:t xml:space="preserve"> be positive.</w:t></w:r></w:p>
+<w:p><m:oMathPara><m:oMath><m:sSup><m:e><m:r><m:t>a</m:t></m:r></m:e><m:sup><m:r><m:t>2</m:t></m:r></m:sup></m:sSup></m:oMath></m:oMathPara></w:p>
+</w:body></w:document>"#;
+        let doc = Document::from_bytes(docx_with_body(body)).unwrap();
+
+        let options = MarkdownOptions::new().with_formula_style(FormulaStyle::Dollar);
+        assert_eq!(
+            doc.to_markdown_with_options(&options).unwrap().trim(),
+            "Let $x$ be positive.\n\n$$a^{2}$$"
+        );
+        let markdown = doc
+            .to_markdown_with_options(&options.clone().with_styles(false))
+            .unwrap();
+        assert_eq!(markdown.trim(), "Let $x$ be positive.\n\n$$a^{2}$$");
+        let markdown = doc.to_markdown().unwrap();
+        assert!(markdown.contains("Let \\(x\\) be positive."));
+        assert!(markdown.contains("\\[a^{2}\\]"));
     }
 
     #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_from_bytes_invalid_data() {
-        let bytes = b"This is not a valid document file".to_vec();
-        let result = Document::from_bytes(bytes);
-        assert!(result.is_err(), "Expected error for invalid data");
+    #[cfg(feature = "ole")]
+    fn test_document_revisions_doc_without_changes() {
+        let doc = Document::open(test_data_path().join("ole/doc/footnote.doc")).unwrap();
+        assert!(doc.revisions().unwrap().is_empty());
+        let accepted = doc.text_with_revisions(RevisionMode::AcceptAll).unwrap();
+        assert_eq!(
+            accepted,
+            doc.text_with_revisions(RevisionMode::ShowMarkup).unwrap()
+        );
+        assert!(accepted.starts_with("\u{5}Test text"));
     }
 
     #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_complex_lists_docx() {
-        let path = test_data_path().join("ooxml/docx/ComplexNumberedLists.docx");
-        let doc = Document::open(&path).expect("Failed to open DOCX");
-        let text = doc.text().expect("Failed to extract text");
-        assert!(!text.is_empty(), "Expected non-empty text");
+    #[cfg(feature = "ole")]
+    fn test_document_notes_doc() {
+        let doc = Document::open(test_data_path().join("ole/doc/footnote.doc")).unwrap();
+        let footnotes = doc.footnotes().unwrap();
+        assert_eq!(footnotes.len(), 1);
+        assert_eq!(footnotes[0].id(), 1);
+        assert_eq!(footnotes[0].paragraph_index(), Some(0));
+        assert_eq!(footnotes[0].text().unwrap(), "TestFootnote");
+
+        let endnotes = doc.endnotes().unwrap();
+        assert_eq!(endnotes.len(), 1);
+        assert_eq!(endnotes[0].kind(), NoteKind::Endnote);
+        assert_eq!(endnotes[0].text().unwrap(), "TestEndnote");
+    }
 
-        let paragraphs = doc.paragraphs().expect("Failed to get paragraphs");
-        assert!(
-            !paragraphs.is_empty(),
-            "Expected paragraphs in list document"
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_headers_footers_doc() {
+        let doc = Document::open(test_data_path().join("ole/doc/ThreeColHeadFoot.doc")).unwrap();
+
+        let headers = doc.headers().unwrap();
+        assert_eq!(headers.len(), 1);
+        assert_eq!(headers[0].section_index(), 0);
+        assert_eq!(headers[0].header_footer_type(), HeaderFooterType::Default);
+        assert_eq!(
+            headers[0].text().unwrap(),
+            "First header column!\tMid header Right header!"
+        );
+
+        let footers = doc.footers().unwrap();
+        assert_eq!(footers.len(), 1);
+        assert_eq!(
+            footers[0].text().unwrap(),
+            "Footer Left\tFooter Middle Footer Right"
         );
     }
 
     #[test]
     #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_footnotes_docx() {
-        let path = test_data_path().join("ooxml/docx/footnotes.docx");
-        let doc = Document::open(&path).expect("Failed to open DOCX");
-        let text = doc.text().expect("Failed to extract text");
-        assert!(!text.is_empty(), "Expected non-empty text");
+    fn test_document_embedded_objects_docx() {
+        let doc = Document::open(test_data_path().join("ooxml/docx/drawing.docx")).unwrap();
+
+        let objects = doc.embedded_objects().unwrap();
+        assert_eq!(objects.len(), 1);
+        let object = &objects[0];
+        assert_eq!(object.prog_id(), Some("Package"));
+        assert_eq!(object.clsid(), Some("0003000C-0000-0000-C000-000000000046"));
+        assert_eq!(object.file_name(), Some("CorelDRAW.csl"));
+        assert_eq!(object.data().len(), 16384);
+        assert!(object.payload().unwrap().starts_with(b"RIFF"));
+        assert_eq!(
+            object.preview_format(),
+            Some(crate::document::PreviewFormat::Wmf)
+        );
     }
 
     #[test]
-    #[cfg(all(feature = "ooxml", feature = "ole"))]
-    fn test_document_endnotes_docx() {
-        let path = test_data_path().join("ooxml/docx/endnotes.docx");
-        let doc = Document::open(&path).expect("Failed to open DOCX");
-        let text = doc.text().expect("Failed to extract text");
-        assert!(!text.is_empty(), "Expected non-empty text");
+    #[cfg(feature = "ole")]
+    fn test_document_embedded_objects_doc() {
+        let doc = Document::open(test_data_path().join("ole/doc/equation.doc")).unwrap();
+
+        let objects = doc.embedded_objects().unwrap();
+        assert_eq!(objects.len(), 1);
+        let object = &objects[0];
+        assert_eq!(object.clsid(), Some("D2D59CD1-0A6A-4D36-AE20-47817077D57C"));
+        // The storage is copied into a standalone compound file
+        assert!(crate::ole::is_ole_file(object.data()));
+        // LibreOffice keeps the formula's ODF package in the storage
+        assert!(object.payload().unwrap().starts_with(b"PK\x03\x04"));
+        assert!(object.preview().is_none());
     }
 
     #[test]
@@ -899,18 +2921,56 @@ mod tests {
     fn test_document_headers_docx() {
         let path = test_data_path().join("ooxml/docx/Headers.docx");
         let doc = Document::open(&path).expect("Failed to open DOCX");
-        // Just verify the file opens and text extraction doesn't fail
         // Note: Headers-only documents may have empty body text
         let _text = doc.text().expect("Failed to extract text");
+
+        // Each of the three sections declares its own default header
+        let headers = doc.headers().unwrap();
+        let sections: Vec<(usize, HeaderFooterType, String)> = headers
+            .iter()
+            .map(|h| (h.section_index(), h.header_footer_type(), h.text().unwrap()))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (0, HeaderFooterType::Default, "Section 1".to_string()),
+                (1, HeaderFooterType::Default, "Section 2".to_string()),
+                (2, HeaderFooterType::Default, "Section 3".to_string()),
+            ]
+        );
     }
 
     #[test]
     #[cfg(all(feature = "ooxml", feature = "ole"))]
     fn test_document_header_footer_docx() {
+        use crate::markdown::{MarkdownOptions, ToMarkdown};
+
         let path = test_data_path().join("ooxml/docx/headerFooter.docx");
         let doc = Document::open(&path).expect("Failed to open DOCX");
         let _text = doc.text().expect("Failed to extract text");
-        // Header/footer documents may have minimal body text
+
+        // Even and first page headers exist but are empty
+        let headers = doc.headers().unwrap();
+        assert_eq!(headers.len(), 3);
+        let default = headers
+            .iter()
+            .find(|h| h.header_footer_type() == HeaderFooterType::Default)
+            .unwrap();
+        assert!(default.text().unwrap().starts_with("This is a simple"));
+        let footers = doc.footers().unwrap();
+        assert!(
+            footers
+                .iter()
+                .any(|f| f.text().unwrap().ends_with("is a simple footer."))
+        );
+
+        let markdown = doc
+            .to_markdown_with_options(&MarkdownOptions::new().with_headers_footers(true))
+            .unwrap();
+        assert!(markdown.starts_with("This is a simple"));
+        assert!(markdown.contains("\n\n---\n\n"));
+        assert!(markdown.trim_end().ends_with("is a simple footer."));
+        assert!(!doc.to_markdown().unwrap().contains("simple footer"));
     }
 
     #[test]
@@ -930,6 +2990,177 @@ mod tests {
         assert!(!text.is_empty(), "Expected non-empty text");
     }
 
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_floating_text_docx() {
+        let doc = Document::open(test_data_path().join("ooxml/docx/text_boxes.docx")).unwrap();
+
+        // The VML fallback of the DrawingML text box is not reported again
+        let shapes = doc.floating_text().unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert_eq!(shapes[0].paragraph_index(), 1);
+        assert_eq!(shapes[0].name(), Some("Text Box 1"));
+        assert_eq!(shapes[0].paragraphs().len(), 2);
+        assert_eq!(shapes[0].text().unwrap(), "Best quarter yet\nSays the CFO");
+        assert_eq!(shapes[1].paragraph_index(), 3);
+        assert_eq!(shapes[1].name(), None);
+        assert_eq!(shapes[1].text().unwrap(), "See the appendix");
+
+        // Shape text stays out of the body text and paragraphs
+        assert!(!doc.text().unwrap().contains("quarter yet"));
+        assert_eq!(doc.paragraph_count().unwrap(), 5);
+        let paragraphs = doc.paragraphs().unwrap();
+        assert_eq!(
+            paragraphs[1].text().unwrap(),
+            "Revenue grew strongly this quarter."
+        );
+
+        assert_eq!(
+            doc.text_with_options(&TextOptions::new()).unwrap(),
+            "Quarterly summary\nRevenue grew strongly this quarter.\nCosts were flat.\n\
+             Details follow.\nEnd of summary"
+        );
+        assert_eq!(
+            doc.text_with_options(&TextOptions::new().with_floating_text(true))
+                .unwrap(),
+            "Quarterly summary\nRevenue grew strongly this quarter.\nBest quarter yet\n\
+             Says the CFO\nCosts were flat.\nDetails follow.\nSee the appendix\nEnd of summary"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_floating_text_markdown() {
+        use crate::markdown::{MarkdownOptions, ToMarkdown};
+
+        let doc = Document::open(test_data_path().join("ooxml/docx/text_boxes.docx")).unwrap();
+        for parallel in [true, false] {
+            let options = MarkdownOptions::new().with_parallel(parallel);
+            let markdown = doc.to_markdown_with_options(&options).unwrap();
+            assert!(markdown.contains(
+                "Revenue grew strongly this quarter.\n\n> Best quarter yet\n>\n> Says the CFO\n\n\
+                 Costs were flat."
+            ));
+            assert!(markdown.contains("Details follow.\n\n> See the appendix\n\nEnd of summary"));
+
+            let markdown = doc
+                .to_markdown_with_options(&options.with_floating_text(false))
+                .unwrap();
+            assert!(!markdown.contains("quarter yet"));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_smartart_docx() {
+        use crate::markdown::{MarkdownOptions, ToMarkdown};
+
+        let doc = Document::open(test_data_path().join("ooxml/docx/smartart.docx")).unwrap();
+        let diagrams = doc.smartart().unwrap();
+        assert_eq!(diagrams.len(), 1);
+        let nodes = diagrams[0].nodes();
+        assert_eq!(nodes.len(), 2);
+        let children: Vec<_> = nodes[0].children.iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(children, ["Scope", "Budget\nReview", "Reviewer"]);
+        assert_eq!(nodes[1].children[0].children[0].text, "Tests & fixes");
+
+        // Node text is on lines of its own where the diagram is anchored
+        assert_eq!(
+            doc.text().unwrap(),
+            "Project plan\nDesign\nScope\nBudget\nReview\nReviewer\nBuild\nPrototype\n\
+             Tests & fixes\nNext steps"
+        );
+        assert_eq!(
+            doc.text_with_options(&TextOptions::new()).unwrap(),
+            "Project plan\n\nDesign\nScope\nBudget\nReview\nReviewer\nBuild\nPrototype\n\
+             Tests & fixes\nNext steps"
+        );
+
+        for parallel in [true, false] {
+            let options = MarkdownOptions::new().with_parallel(parallel);
+            let markdown = doc.to_markdown_with_options(&options).unwrap();
+            assert!(markdown.contains(
+                "- Design\n  - Scope\n  - Budget Review\n  - Reviewer\n- Build\n  - Prototype\n    \
+                 - Tests & fixes\n\nNext steps"
+            ));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_whitespace_docx() {
+        let doc = Document::open(test_data_path().join("ooxml/docx/whitespace.docx")).unwrap();
+        let expected = "Name:\tValue\nLine one\nLine two\nLine three\n\
+                        well\u{2011}known hyphen & co\na   b c\nBefore\nAfter";
+        assert_eq!(doc.text().unwrap(), expected);
+        assert_eq!(
+            doc.text_with_options(&TextOptions::new()).unwrap(),
+            expected
+        );
+
+        let paragraphs = doc.paragraphs().unwrap();
+        assert_eq!(
+            paragraphs[1].text().unwrap(),
+            "Line one\nLine two\nLine three"
+        );
+        let runs = paragraphs[3].runs().unwrap();
+        assert_eq!(runs[0].text().unwrap(), "a   b ");
+
+        let options = TextOptions::new()
+            .with_paragraph_separator("\n\n")
+            .with_preserve_whitespace(false);
+        assert_eq!(
+            doc.text_with_options(&options).unwrap(),
+            "Name: Value\n\nLine one Line two Line three\n\nwell\u{2011}known hyphen & co\n\n\
+             a b c\n\nBefore After"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "odf")]
+    fn test_document_whitespace_odt() {
+        let doc = Document::open(test_data_path().join("odf/whitespace.odt")).unwrap();
+        // Source line breaks and indentation collapse; `text:s` spaces do not
+        let expected = "Name:\tValue\nLine one\nLine two\nwell\u{2011}known hyphen & co\n\
+                        a   b c\nWrapped source line";
+        assert_eq!(doc.text().unwrap(), expected);
+        assert_eq!(
+            doc.text_with_options(&TextOptions::new()).unwrap(),
+            expected
+        );
+
+        let paragraphs = doc.paragraphs().unwrap();
+        let runs: Vec<String> = paragraphs[3]
+            .runs()
+            .unwrap()
+            .iter()
+            .map(|run| run.text().unwrap())
+            .collect();
+        assert_eq!(runs, ["a   b", " c"]);
+
+        let options = TextOptions::new()
+            .with_paragraph_separator("\n\n")
+            .with_preserve_whitespace(false);
+        assert_eq!(
+            doc.text_with_options(&options).unwrap(),
+            "Name: Value\n\nLine one Line two\n\nwell\u{2011}known hyphen & co\n\na b c\n\n\
+             Wrapped source line"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_floating_text_doc() {
+        // Floating pictures are shapes without a text box story
+        let doc = Document::open(test_data_path().join("ole/doc/FloatingPictures.doc")).unwrap();
+        assert!(doc.floating_text().unwrap().is_empty());
+        assert_eq!(
+            doc.text_with_options(&TextOptions::new().with_floating_text(true))
+                .unwrap(),
+            doc.text_with_options(&TextOptions::new()).unwrap()
+        );
+    }
+
     #[test]
     #[cfg(feature = "rtf")]
     fn test_document_rtf_encodings() {
@@ -978,4 +3209,745 @@ mod tests {
             assert!(row_count > 0, "Table should have at least one row");
         }
     }
+
+    #[test]
+    #[cfg(feature = "rtf")]
+    fn test_document_rtf_renamed_and_compressed() {
+        let rtf = b"{\\rtf1\\ansi\\ansicpg1252 Plain {\\b bold} {\\i caf\\'e9}\\par\n\\trowd\\cellx1000\\cellx2000\\pard\\intbl A\\cell B\\cell\\row\n}";
+
+        // Word happily saves RTF with a .doc extension; content decides the format
+        let path = std::env::temp_dir().join("litchi_rtf_renamed.doc");
+        std::fs::write(&path, rtf).unwrap();
+        let doc = Document::open(&path).expect("Failed to open renamed RTF");
+        std::fs::remove_file(&path).ok();
+
+        let text = doc.text().unwrap();
+        assert!(text.contains("café"), "{text:?}");
+        let runs = doc.paragraphs().unwrap()[0].runs().unwrap();
+        let bold = runs.iter().find(|r| r.text().unwrap() == "bold").unwrap();
+        assert_eq!(bold.bold().unwrap(), Some(true));
+        let italic = runs.iter().find(|r| r.text().unwrap() == "café").unwrap();
+        assert_eq!(italic.italic().unwrap(), Some(true));
+        assert_eq!(doc.tables().unwrap().len(), 1);
+
+        // LZFu sample from [MS-OXRTFCP] 4.1
+        let compressed = vec![
+            0x2d, 0x00, 0x00, 0x00, 0x2b, 0x00, 0x00, 0x00, 0x4c, 0x5a, 0x46, 0x75, 0xf1, 0xc5,
+            0xc7, 0xa7, 0x03, 0x00, 0x0a, 0x00, 0x72, 0x63, 0x70, 0x67, 0x31, 0x32, 0x35, 0x42,
+            0x32, 0x0a, 0xf3, 0x20, 0x68, 0x65, 0x6c, 0x09, 0x00, 0x20, 0x62, 0x77, 0x05, 0xb0,
+            0x6c, 0x64, 0x7d, 0x0a, 0x80, 0x0f, 0xa0,
+        ];
+        let doc = Document::from_bytes(compressed).expect("Failed to open compressed RTF");
+        assert!(doc.text().unwrap().contains("hello world"));
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_from_corrupted_doc_bytes() {
+        // Truncated and corrupted files must fail with an error, not panic
+        let data = std::fs::read(test_data_path().join("ole/doc/FancyFoot.doc")).unwrap();
+        for len in (0..data.len()).step_by(1024) {
+            if let Ok(doc) = Document::from_bytes(data[..len].to_vec()) {
+                let _ = doc.text();
+            }
+        }
+
+        let mut mutator = crate::ole::ByteMutator::new(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..200 {
+            let mut corrupted = data.clone();
+            mutator.mutate(&mut corrupted, 16);
+            if let Ok(doc) = Document::from_bytes(corrupted) {
+                let _ = doc.text();
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_rejects_bad_fib_on_open() {
+        let data = std::fs::read(test_data_path().join("ole/doc/FancyFoot.doc")).unwrap();
+        let doc = Document::from_bytes(data.clone()).unwrap();
+        assert!(!doc.has_macros().unwrap());
+
+        // The WordDocument stream starts on a sector boundary with the FIB magic
+        let fib = (512..data.len())
+            .step_by(512)
+            .find(|&offset| data[offset..offset + 2] == [0xEC, 0xA5])
+            .unwrap();
+        let mut corrupted = data;
+        corrupted[fib] = 0;
+        assert!(Document::from_bytes(corrupted).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_document_sections() {
+        // A Letter portrait section followed by a landscape one
+        let doc = Document::open(test_data_path().join("ole/doc/FloatingPictures.doc")).unwrap();
+        let sections = doc.sections().unwrap();
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].orientation(), PageOrientation::Portrait);
+        assert_eq!(sections[0].page_width(), Some(Length::from_inches(8.5)));
+        assert_eq!(sections[0].page_height(), Some(Length::from_inches(11.0)));
+        assert_eq!(sections[0].margins().left, Some(Length::from_twips(1800)));
+        assert_eq!(sections[1].orientation(), PageOrientation::Landscape);
+        assert_eq!(sections[1].page_width(), Some(Length::from_inches(11.0)));
+        assert_eq!(sections[1].margins().top, Some(Length::from_twips(1800)));
+
+        // A4, set by the file rather than assumed
+        let doc = Document::open(test_data_path().join("ooxml/docx/Headers.docx")).unwrap();
+        let sections = doc.sections().unwrap();
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].page_width(), Some(Length::from_twips(11906)));
+        assert_eq!(sections[0].page_height(), Some(Length::from_twips(16838)));
+        assert_eq!(sections[0].orientation(), PageOrientation::Portrait);
+        assert_eq!(sections[0].margins().right, Some(Length::from_twips(850)));
+
+        // Properties the section does not set are unknown
+        let doc = Document::open(test_data_path().join("ole/doc/Cp1251.doc")).unwrap();
+        let sections = doc.sections().unwrap();
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].page_width(), None);
+        assert_eq!(sections[0].margins(), &PageMargins::default());
+    }
+
+    /// Debug strings of the events `doc` reports, stopping once `stop`
+    /// returns true for an event.
+    fn walk_events(
+        doc: &Document,
+        mut stop: impl FnMut(&TextEvent<'_>) -> bool,
+    ) -> (ControlFlow<()>, Vec<String>) {
+        let mut events = Vec::new();
+        let flow = doc
+            .walk_text(|event| {
+                events.push(format!("{event:?}"));
+                if stop(&event) {
+                    ControlFlow::Break(())
+                } else {
+                    ControlFlow::Continue(())
+                }
+            })
+            .unwrap();
+        (flow, events)
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_walk_text_docx() {
+        let body = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:pPr><w:tabs><w:tab w:val="left" w:pos="720"/></w:tabs></w:pPr><w:r><w:t xml:space="preserve">Fish &amp; chips</w:t><w:tab/><w:t>4</w:t></w:r></w:p>
+<w:tbl><w:tr><w:tc><w:p><w:r><w:t>A1</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>B1</w:t></w:r></w:p></w:tc></w:tr></w:tbl>
+<w:p><w:r><w:t>After</w:t></w:r></w:p>
+</w:body></w:document>"#;
+        let doc = Document::from_bytes(docx_with_body(body)).unwrap();
+
+        let (flow, events) = walk_events(&doc, |_| false);
+        assert!(flow.is_continue());
+        assert_eq!(
+            events,
+            [
+                "ParagraphStart",
+                "Text(\"Fish \")",
+                "Text(\"&\")",
+                "Text(\" chips\")",
+                "Text(\"\\t\")",
+                "Text(\"4\")",
+                "TableStart",
+                "TableRowStart",
+                "TableCellStart",
+                "ParagraphStart",
+                "Text(\"A1\")",
+                "TableCellStart",
+                "ParagraphStart",
+                "Text(\"B1\")",
+                "TableEnd",
+                "ParagraphStart",
+                "Text(\"After\")",
+            ]
+        );
+
+        // Nothing is reported after the visitor stops
+        let (flow, events) = walk_events(&doc, |event| *event == TextEvent::Text("A1"));
+        assert!(flow.is_break());
+        assert_eq!(events.last().unwrap(), "Text(\"A1\")");
+        assert_eq!(events.len(), 11);
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_walk_text_doc() {
+        let doc = Document::open(test_data_path().join("ole/doc/FancyFoot.doc")).unwrap();
+
+        let (flow, events) = walk_events(&doc, |_| false);
+        assert!(flow.is_continue());
+        let paragraphs = events.iter().filter(|e| *e == "ParagraphStart").count();
+        assert!(paragraphs > 1);
+        assert!(events.iter().any(|e| e.starts_with("Text(")));
+
+        let (flow, events) = walk_events(&doc, |event| matches!(event, TextEvent::Text(_)));
+        assert!(flow.is_break());
+        assert!(events.last().unwrap().starts_with("Text("));
+        assert_eq!(events.iter().filter(|e| e.starts_with("Text(")).count(), 1);
+    }
+
+    /// Open a .doc file over a reader that logs the byte ranges read after opening.
+    #[cfg(feature = "ole")]
+    fn counted_doc(
+        bytes: &[u8],
+    ) -> (
+        DocFile<ole::CountingReader<std::io::Cursor<Vec<u8>>>>,
+        ole::ReadLog,
+    ) {
+        let reader = ole::CountingReader::new(std::io::Cursor::new(bytes.to_vec()));
+        let reads = reader.reads();
+        let ole_file = ole::OleFile::open(reader).unwrap();
+        let file = DocFile::new(ole::doc::Package::from_ole_file(ole_file).unwrap());
+        reads.borrow_mut().clear();
+        (file, reads)
+    }
+
+    /// Debug strings of the events the parsed body of `doc` reports.
+    #[cfg(feature = "ole")]
+    fn parsed_doc_events<R: std::io::Read + std::io::Seek>(doc: &DocFile<R>) -> Vec<String> {
+        let mut events = Vec::new();
+        let flow = walk_elements(
+            &doc.document().unwrap().body_elements().unwrap(),
+            &mut |event| {
+                events.push(format!("{event:?}"));
+                ControlFlow::Continue(())
+            },
+        )
+        .unwrap();
+        assert!(flow.is_continue());
+        events
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_walk_text_doc_stops_reading_after_break() {
+        let mut writer = ole::doc::DocWriter::new();
+        for i in 0..2000 {
+            writer
+                .add_paragraph(&format!(
+                    "Paragraph {i:04} of a document spanning many sectors"
+                ))
+                .unwrap();
+        }
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        writer.write_to(&mut bytes).unwrap();
+        let bytes = bytes.into_inner();
+
+        // Where a paragraph near the end is stored in the file
+        let needle = "Paragraph 1500";
+        let utf16: Vec<u8> = needle.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let position = [needle.as_bytes(), &utf16]
+            .iter()
+            .find_map(|needle| bytes.windows(needle.len()).position(|w| w == *needle))
+            .unwrap() as u64;
+        let reads_position =
+            |reads: &[std::ops::Range<u64>]| reads.iter().any(|read| read.contains(&position));
+
+        // Stopping at the first text leaves the rest of the text unread
+        let (file, reads) = counted_doc(&bytes);
+        let mut texts = Vec::new();
+        let flow = walk_doc(&file, &mut |event| match event {
+            TextEvent::Text(text) => {
+                texts.push(text.to_string());
+                ControlFlow::Break(())
+            },
+            _ => ControlFlow::Continue(()),
+        })
+        .unwrap();
+        assert!(flow.is_break());
+        assert_eq!(
+            texts,
+            ["Paragraph 0000 of a document spanning many sectors"]
+        );
+        assert!(!reads_position(&reads.borrow()));
+        let read: u64 = reads
+            .borrow()
+            .iter()
+            .map(|read| read.end - read.start)
+            .sum();
+        assert!(
+            read < bytes.len() as u64 / 4,
+            "read {read} of {} bytes",
+            bytes.len()
+        );
+
+        // A full walk reads it, and reports what the parsed document does
+        let (file, reads) = counted_doc(&bytes);
+        let mut streamed = Vec::new();
+        let flow = walk_doc(&file, &mut |event| {
+            streamed.push(format!("{event:?}"));
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert!(flow.is_continue());
+        assert!(reads_position(&reads.borrow()));
+        assert_eq!(
+            streamed.iter().filter(|e| *e == "ParagraphStart").count(),
+            2000
+        );
+        assert_eq!(streamed, parsed_doc_events(&file));
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_walk_text_doc_matches_parsed() {
+        let mut paths: Vec<_> = std::fs::read_dir(test_data_path().join("ole/doc"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| !path.ends_with("PasswordProtected.doc"))
+            .collect();
+        paths.sort();
+        for path in paths {
+            let doc = Document::open(&path).unwrap();
+            let DocumentImpl::Doc(file, _) = &doc.inner else {
+                panic!("{} is not a .doc file", path.display());
+            };
+
+            let mut streamed = Vec::new();
+            let flow = walk_doc(file, &mut |event| {
+                streamed.push(format!("{event:?}"));
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+            assert!(flow.is_continue());
+            assert_eq!(streamed, parsed_doc_events(file), "{}", path.display());
+        }
+    }
+
+    /// Assert that `computed` is within `percent` of the count Word reported.
+    fn assert_close(computed: usize, reported: Option<u32>, percent: usize) {
+        let reported = reported.unwrap() as usize;
+        let tolerance = reported * percent / 100;
+        assert!(
+            computed.abs_diff(reported) <= tolerance,
+            "computed {computed}, reported {reported}"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_statistics_docx() {
+        let doc = Document::open(test_data_path().join("ooxml/docx/nested_tables.docx")).unwrap();
+        let stats = doc.statistics().unwrap();
+        assert_eq!(stats.word_count(), 24);
+        assert_eq!(stats.reported_word_count(), Some(24));
+        // Word reports characters without spaces
+        assert_eq!(stats.character_count_no_spaces(), 108);
+        assert_eq!(stats.reported_character_count(), Some(108));
+        assert_eq!(stats.character_count(), 117);
+        assert_eq!(stats.paragraph_count(), 15);
+        assert_eq!(stats.page_count(), Some(1));
+
+        let path = "ooxml/docx/documentProtection_forms_no_password.docx";
+        let stats = Document::open(test_data_path().join(path))
+            .unwrap()
+            .statistics()
+            .unwrap();
+        assert_eq!(stats.word_count(), 4);
+        assert_eq!(stats.reported_word_count(), Some(4));
+        assert_eq!(stats.character_count_no_spaces(), 26);
+        assert_eq!(stats.reported_character_count(), Some(26));
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_statistics_doc() {
+        let path = test_data_path().join("ole/doc/HeaderFooterProblematic.doc");
+        let stats = Document::open(path).unwrap().statistics().unwrap();
+        assert_close(stats.word_count(), stats.reported_word_count(), 10);
+        assert_close(
+            stats.character_count_no_spaces(),
+            stats.reported_character_count(),
+            2,
+        );
+        assert_eq!(stats.page_count(), Some(1));
+
+        // Each CJK ideograph is a word
+        let stats = Document::open(test_data_path().join("ole/doc/cjklist30.doc"))
+            .unwrap()
+            .statistics()
+            .unwrap();
+        assert_close(stats.word_count(), stats.reported_word_count(), 10);
+        assert_close(
+            stats.character_count_no_spaces(),
+            stats.reported_character_count(),
+            2,
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "ole", feature = "ooxml"))]
+    fn test_document_statistics_doc_matches_docx() {
+        // Headers, footers and field codes of the .doc are not counted
+        let doc = Document::open(test_data_path().join("ole/doc/FancyFoot.doc")).unwrap();
+        let docx = Document::open(test_data_path().join("ooxml/docx/FancyFoot.docx")).unwrap();
+        let doc_stats = doc.statistics().unwrap();
+        let docx_stats = docx.statistics().unwrap();
+        assert_eq!(doc_stats.word_count(), docx_stats.word_count());
+        assert_eq!(doc_stats.character_count(), docx_stats.character_count());
+        assert_eq!(
+            doc_stats.character_count_no_spaces(),
+            docx_stats.character_count_no_spaces()
+        );
+        assert_eq!(doc_stats.paragraph_count(), docx_stats.paragraph_count());
+        assert_eq!(doc_stats.page_count(), Some(2));
+    }
+
+    #[test]
+    #[cfg(feature = "odf")]
+    fn test_document_statistics_odt() {
+        let mut builder = crate::odf::DocumentBuilder::new();
+        builder.add_heading("Menu", 1).unwrap();
+        builder.add_paragraph("Fish & chips cost £4.").unwrap();
+        builder.add_paragraph("").unwrap();
+        builder.add_paragraph("日本語").unwrap();
+        let doc = Document::from_bytes(builder.build().unwrap()).unwrap();
+
+        let stats = doc.statistics().unwrap();
+        assert_eq!(stats.word_count(), 8);
+        assert_eq!(stats.character_count(), 28);
+        assert_eq!(stats.character_count_no_spaces(), 24);
+        assert_eq!(stats.paragraph_count(), 3);
+        assert_eq!(stats.page_count(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "rtf")]
+    fn test_document_statistics_rtf() {
+        let rtf = br"{\rtf1\ansi{\b Menu}\par Fish & chips cost \'a34.\par\par\u26085?\u26412?\u35486?\par}";
+        let stats = Document::from_bytes(rtf.to_vec())
+            .unwrap()
+            .statistics()
+            .unwrap();
+        assert_eq!(stats.word_count(), 8);
+        assert_eq!(stats.character_count(), 28);
+        assert_eq!(stats.character_count_no_spaces(), 24);
+        assert_eq!(stats.paragraph_count(), 3);
+        assert_eq!(stats.reported_word_count(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_language_docx() {
+        let doc =
+            Document::open(test_data_path().join("ooxml/docx/documentProperties.docx")).unwrap();
+        // docDefaults declare en-US, with Arabic for complex scripts
+        let defaults = doc.default_language().unwrap();
+        assert_eq!(defaults.latin.as_deref(), Some("en-US"));
+        assert_eq!(defaults.east_asian.as_deref(), Some("en-US"));
+        assert_eq!(defaults.complex.as_deref(), Some("ar-SA"));
+
+        // The runs only override the Latin language
+        let paragraphs = doc.paragraphs().unwrap();
+        let run = &paragraphs[0].runs().unwrap()[0];
+        assert_eq!(run.text().unwrap(), "Hello World");
+        let language = run.language().unwrap();
+        assert_eq!(language.latin.as_deref(), Some("it-IT"));
+        assert_eq!(language.east_asian.as_deref(), Some("en-US"));
+        assert_eq!(language.complex.as_deref(), Some("ar-SA"));
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_document_language_doc() {
+        let doc = Document::open(test_data_path().join("ole/doc/cjklist30.doc")).unwrap();
+        let expected = crate::common::Language {
+            latin: Some("en-US".to_string()),
+            east_asian: Some("zh-TW".to_string()),
+            complex: Some("ar-SA".to_string()),
+        };
+        assert_eq!(doc.default_language().unwrap(), expected);
+        let paragraphs = doc.paragraphs().unwrap();
+        let run = &paragraphs[0].runs().unwrap()[0];
+        assert_eq!(run.language().unwrap(), expected);
+
+        // No language at all is not an error
+        let doc = Document::open(test_data_path().join("ole/doc/Cp1251.doc")).unwrap();
+        assert!(doc.default_language().unwrap().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "rtf")]
+    fn test_document_language_rtf() {
+        let rtf = br"{\rtf1\ansi\deflang1033\deflangfe2052 Hello {\lang1031 Hallo}\par}";
+        let doc = Document::from_bytes(rtf.to_vec()).unwrap();
+        let defaults = doc.default_language().unwrap();
+        assert_eq!(defaults.latin.as_deref(), Some("en-US"));
+        assert_eq!(defaults.east_asian.as_deref(), Some("zh-CN"));
+        assert_eq!(defaults.complex, None);
+
+        let paragraphs = doc.paragraphs().unwrap();
+        let runs = paragraphs[0].runs().unwrap();
+        let languages: Vec<_> = runs
+            .iter()
+            .map(|run| run.language().unwrap().latin)
+            .collect();
+        assert_eq!(
+            languages,
+            [Some("en-US".to_string()), Some("de-DE".to_string())]
+        );
+        assert_eq!(
+            runs[1].language().unwrap().east_asian.as_deref(),
+            Some("zh-CN")
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "odf")]
+    fn test_document_language_odt() {
+        use std::io::{Cursor, Read, Write};
+
+        let mut builder = crate::odf::DocumentBuilder::new();
+        builder.add_paragraph("placeholder").unwrap();
+        let source = builder.build().unwrap();
+
+        // Add language styles, which the builder does not write
+        let default_style = r#"<office:styles><style:default-style style:family="paragraph"><style:text-properties fo:language="en" fo:country="GB" style:language-asian="zh" style:country-asian="CN"/></style:default-style>"#;
+        let automatic_styles = r#"<office:automatic-styles><style:style style:name="P1" style:family="paragraph"><style:text-properties fo:language="fr" fo:country="FR"/></style:style><style:style style:name="T1" style:family="text"><style:text-properties style:language-asian="ja" style:country-asian="JP"/></style:style></office:automatic-styles>"#;
+        let body = r#"<office:text><text:p>Hello</text:p><text:p text:style-name="P1">Bonjour <text:span text:style-name="T1">日本</text:span></text:p></office:text>"#;
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(source)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            let name = entry.name().to_string();
+            let mut data = String::new();
+            entry.read_to_string(&mut data).unwrap();
+            let data = match name.as_str() {
+                "styles.xml" => data.replace("<office:styles>", default_style),
+                "content.xml" => {
+                    let start = data.find("<office:text>").unwrap();
+                    let end = data.find("</office:text>").unwrap() + "</office:text>".len();
+                    data[..start].replace("<office:automatic-styles/>", automatic_styles)
+                        + body
+                        + &data[end..]
+                },
+                _ => data,
+            };
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Stored);
+            writer.start_file(name.as_str(), options).unwrap();
+            writer.write_all(data.as_bytes()).unwrap();
+        }
+        let doc = Document::from_bytes(writer.finish().unwrap().into_inner()).unwrap();
+
+        let defaults = doc.default_language().unwrap();
+        assert_eq!(defaults.latin.as_deref(), Some("en-GB"));
+        assert_eq!(defaults.east_asian.as_deref(), Some("zh-CN"));
+
+        let paragraphs = doc.paragraphs().unwrap();
+        let run = &paragraphs[0].runs().unwrap()[0];
+        assert_eq!(run.language().unwrap(), defaults);
+
+        let runs = paragraphs[1].runs().unwrap();
+        let languages: Vec<_> = runs
+            .iter()
+            .map(|run| {
+                let language = run.language().unwrap();
+                (language.latin, language.east_asian)
+            })
+            .collect();
+        assert_eq!(
+            languages,
+            [
+                (Some("fr-FR".to_string()), Some("zh-CN".to_string())),
+                (Some("fr-FR".to_string()), Some("ja-JP".to_string())),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_bidi_docx() {
+        use super::super::Direction;
+        use crate::markdown::{MarkdownOptions, TableStyle, ToMarkdown};
+
+        // Arabic paragraphs with embedded English and numbers, and an
+        // English paragraph with an embedded Arabic word
+        let doc = Document::open(test_data_path().join("ooxml/docx/bidi.docx")).unwrap();
+        let paragraphs = doc.paragraphs().unwrap();
+        let directions: Vec<Direction> = paragraphs
+            .iter()
+            .map(|para| para.direction().unwrap())
+            .collect();
+        assert_eq!(
+            directions[..3],
+            [
+                Direction::RightToLeft,
+                Direction::LeftToRight,
+                Direction::RightToLeft
+            ]
+        );
+        let runs: Vec<(String, bool)> = paragraphs[0]
+            .runs()
+            .unwrap()
+            .iter()
+            .map(|run| (run.text().unwrap(), run.is_rtl().unwrap()))
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                ("يدعم ".to_string(), true),
+                ("Microsoft Word".to_string(), false),
+                (" أكثر من 100 لغة.".to_string(), true),
+            ]
+        );
+
+        // Text stays in logical order, with isolates only when asked
+        let text = doc.text().unwrap();
+        assert!(text.starts_with("يدعم Microsoft Word أكثر من 100 لغة.\n"));
+        assert!(!text.contains('\u{2068}'));
+        let options = TextOptions::new().with_bidi_isolates(true);
+        let lines: Vec<String> = doc
+            .text_with_options(&options)
+            .unwrap()
+            .lines()
+            .map(str::to_string)
+            .collect();
+        assert_eq!(
+            lines[..3],
+            [
+                "يدعم \u{2068}Microsoft Word\u{2069} أكثر من 100 لغة.",
+                "The Arabic word for book is \u{2068}كتاب\u{2069}.",
+                "رقم الهاتف: \u{2068}+1 555 0100\u{2069}",
+            ]
+        );
+
+        // HTML output marks right-to-left paragraphs and cells
+        let options = MarkdownOptions::new().with_table_style(TableStyle::MinimalHtml);
+        let markdown = doc.to_markdown_with_options(&options).unwrap();
+        assert!(markdown.starts_with(
+            "<div dir=\"rtl\">\n\nيدعم Microsoft Word أكثر من 100 لغة.\n\n</div>\n\n\
+             The Arabic word for book is كتاب.\n\n"
+        ));
+        assert!(markdown.contains("<th><div dir=\"rtl\">كتاب</div></th><th>Book</th>"));
+        // Plain Markdown stays free of HTML
+        assert!(!doc.to_markdown().unwrap().contains("<div"));
+    }
+
+    #[test]
+    #[cfg(feature = "rtf")]
+    fn test_document_bidi_rtf() {
+        let rtf = br"{\rtf1\ansi\rtlpar{\rtlch abc }{\ltrch Word}\par\pard\ltrpar plain\par}";
+        let doc = Document::from_bytes(rtf.to_vec()).unwrap();
+        let paragraphs = doc.paragraphs().unwrap();
+        assert!(paragraphs[0].direction().unwrap().is_rtl());
+        assert!(!paragraphs[1].direction().unwrap().is_rtl());
+        let flags: Vec<bool> = paragraphs[0]
+            .runs()
+            .unwrap()
+            .iter()
+            .map(|run| run.is_rtl().unwrap())
+            .collect();
+        assert_eq!(flags, [true, false]);
+        assert_eq!(
+            doc.text_with_options(&TextOptions::new().with_bidi_isolates(true))
+                .unwrap(),
+            "abc \u{2068}Word\u{2069}\nplain"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_document_protection_docx() {
+        use crate::common::{Protection, ProtectionKind};
+
+        let protection = |name: &str| {
+            Document::open(format!(
+                "test-data/ooxml/docx/documentProtection_{name}.docx"
+            ))
+            .unwrap()
+            .protection()
+            .unwrap()
+        };
+        assert_eq!(protection("no_protection"), Protection::default());
+        assert_eq!(
+            protection("comments_no_password"),
+            Protection::new(ProtectionKind::CommentsOnly)
+        );
+        assert_eq!(
+            protection("forms_no_password"),
+            Protection::new(ProtectionKind::Forms)
+        );
+        assert_eq!(
+            protection("readonly_no_password"),
+            Protection::new(ProtectionKind::ReadOnly)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rtf")]
+    fn test_document_protection_rtf() {
+        use crate::common::{Protection, ProtectionKind};
+
+        let protection = |rtf: &[u8]| {
+            Document::from_bytes(rtf.to_vec())
+                .unwrap()
+                .protection()
+                .unwrap()
+        };
+        assert_eq!(protection(br"{\rtf1\ansi Text\par}"), Protection::default());
+        assert_eq!(
+            protection(br"{\rtf1\ansi\annotprot Text\par}"),
+            Protection::new(ProtectionKind::CommentsOnly)
+        );
+        assert_eq!(
+            protection(
+                br"{\rtf1\ansi{\*\passwordhash 0100000001}\enforceprot1\protlevel2 Text\par}"
+            ),
+            Protection::with_password(ProtectionKind::Forms)
+        );
+        // A protection level that is not enforced does not restrict editing
+        assert_eq!(
+            protection(br"{\rtf1\ansi\enforceprot0\protlevel3 Text\par}"),
+            Protection::default()
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "signatures")]
+    fn test_document_signatures_unsigned() {
+        let doc = Document::open(test_data_path().join("ooxml/docx/footnotes.docx")).unwrap();
+        assert!(doc.signatures().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "odf")]
+    fn test_document_protection_odt() {
+        use crate::common::{Protection, ProtectionKind};
+        use std::io::{Cursor, Read, Write};
+
+        let mut builder = crate::odf::DocumentBuilder::new();
+        builder.add_paragraph("Final draft").unwrap();
+        let source = builder.build().unwrap();
+        let doc = Document::from_bytes(source.clone()).unwrap();
+        assert_eq!(doc.protection().unwrap(), Protection::default());
+
+        // Ask for the document to be opened read-only
+        let settings = r#"<?xml version="1.0" encoding="UTF-8"?><office:document-settings xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:config="urn:oasis:names:tc:opendocument:xmlns:config:1.0"><office:settings><config:config-item-set config:name="ooo:configuration-settings"><config:config-item config:name="LoadReadonly" config:type="boolean">true</config:config-item></config:config-item-set></office:settings></office:document-settings>"#;
+        let mut archive = zip::ZipArchive::new(Cursor::new(source)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Stored);
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            let name = entry.name().to_string();
+            let mut data = String::new();
+            entry.read_to_string(&mut data).unwrap();
+            writer.start_file(name.as_str(), options).unwrap();
+            writer.write_all(data.as_bytes()).unwrap();
+        }
+        writer.start_file("settings.xml", options).unwrap();
+        writer.write_all(settings.as_bytes()).unwrap();
+        let doc = Document::from_bytes(writer.finish().unwrap().into_inner()).unwrap();
+        assert_eq!(
+            doc.protection().unwrap(),
+            Protection::new(ProtectionKind::ReadOnlyRecommended)
+        );
+    }
 }
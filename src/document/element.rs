@@ -28,6 +28,11 @@ use super::{Paragraph, Table};
 /// # Ok::<(), litchi::common::Error>(())
 /// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
 pub enum DocumentElement {
     /// A paragraph element (boxed to reduce enum size)
     Paragraph(Box<Paragraph>),
@@ -0,0 +1,228 @@
+//! Embedded OLE object implementation for Word documents.
+
+#[cfg(feature = "imgconv")]
+use crate::common::{Error, Result};
+
+/// Image format of an embedded object's preview.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PreviewFormat {
+    /// Enhanced Metafile
+    Emf,
+    /// Windows Metafile
+    Wmf,
+    /// PNG image
+    Png,
+}
+
+impl PreviewFormat {
+    /// Detect the format of preview image bytes.
+    pub fn detect(data: &[u8]) -> Option<Self> {
+        let u16_at = |offset: usize| {
+            data.get(offset..offset + 2)
+                .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        };
+
+        if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(Self::Png)
+        } else if data.starts_with(&[0x01, 0x00, 0x00, 0x00]) && data.get(40..44) == Some(b" EMF") {
+            Some(Self::Emf)
+        } else if data.starts_with(&[0xD7, 0xCD, 0xC6, 0x9A])
+            || (matches!(u16_at(0), Some(1 | 2)) && u16_at(2) == Some(9))
+        {
+            // Placeable WMF, or a standard WMF header (memory or disk metafile)
+            Some(Self::Wmf)
+        } else {
+            None
+        }
+    }
+}
+
+/// An OLE object embedded in a document, such as an Excel range, a Visio
+/// drawing or a packaged file.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for object in doc.embedded_objects()? {
+///     println!(
+///         "{} {:?}",
+///         object.prog_id().unwrap_or("unknown"),
+///         object.file_name()
+///     );
+///     // Embedded .xlsx/.docx files can be opened with litchi again
+///     if let Some(payload) = object.payload() {
+///         std::fs::write(object.file_name().unwrap_or("payload.bin"), payload)?;
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct EmbeddedObject {
+    prog_id: Option<String>,
+    clsid: Option<String>,
+    file_name: Option<String>,
+    data: Vec<u8>,
+    payload: Option<Vec<u8>>,
+    preview: Option<Vec<u8>>,
+}
+
+impl EmbeddedObject {
+    /// Create an embedded object from its raw bytes.
+    ///
+    /// Compound file objects are read for their class, native data and
+    /// preview; `prog_id` and `preview`, when given, take precedence over
+    /// what the object itself records. Any other data is taken to be an
+    /// embedded file and becomes the payload.
+    pub(crate) fn new(prog_id: Option<String>, data: Vec<u8>, preview: Option<Vec<u8>>) -> Self {
+        let mut object = Self {
+            prog_id,
+            clsid: None,
+            file_name: None,
+            payload: None,
+            preview,
+            data,
+        };
+
+        #[cfg(feature = "ole")]
+        if crate::ole::is_ole_file(&object.data) {
+            if let Ok(storage) = crate::ole::object::ObjectStorage::parse(&object.data) {
+                object.prog_id = object.prog_id.or(storage.prog_id);
+                object.clsid = storage.clsid;
+                object.file_name = storage.file_name;
+                object.payload = storage.payload;
+                object.preview = object.preview.or(storage.preview);
+            }
+            return object;
+        }
+
+        if object.data.starts_with(b"PK\x03\x04") {
+            object.payload = Some(object.data.clone());
+        }
+        object
+    }
+
+    /// Programmatic identifier of the object's class, e.g. `Excel.Sheet.12`
+    /// or `Package`.
+    #[inline]
+    pub fn prog_id(&self) -> Option<&str> {
+        self.prog_id.as_deref()
+    }
+
+    /// CLSID of the object's class, formatted as
+    /// `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX`.
+    #[inline]
+    pub fn clsid(&self) -> Option<&str> {
+        self.clsid.as_deref()
+    }
+
+    /// Original file name of a packaged file.
+    #[inline]
+    pub fn file_name(&self) -> Option<&str> {
+        self.file_name.as_deref()
+    }
+
+    /// Raw bytes of the object.
+    ///
+    /// This is a compound file holding the object's storage, or the embedded
+    /// file itself for Office Open XML packages embedded in .docx files.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Native content of the object, when it wraps a file.
+    ///
+    /// For "Package" objects this is the packaged file, and for embedded
+    /// Office Open XML documents it is the `.xlsx`/`.docx`/`.pptx` package,
+    /// which can be opened with litchi in turn.
+    #[inline]
+    pub fn payload(&self) -> Option<&[u8]> {
+        self.payload.as_deref()
+    }
+
+    /// Preview image shown in place of the object, usually EMF or WMF.
+    #[inline]
+    pub fn preview(&self) -> Option<&[u8]> {
+        self.preview.as_deref()
+    }
+
+    /// Image format of the preview, if it is recognized.
+    pub fn preview_format(&self) -> Option<PreviewFormat> {
+        self.preview.as_deref().and_then(PreviewFormat::detect)
+    }
+
+    /// Render the preview image as PNG.
+    ///
+    /// Returns `Ok(None)` when the object has no preview.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - Optional output width in pixels
+    /// * `height` - Optional output height in pixels
+    #[cfg(feature = "imgconv")]
+    pub fn preview_to_png(
+        &self,
+        width: Option<u32>,
+        height: Option<u32>,
+    ) -> Result<Option<Vec<u8>>> {
+        let Some(preview) = self.preview.as_deref() else {
+            return Ok(None);
+        };
+        let png = match PreviewFormat::detect(preview) {
+            Some(PreviewFormat::Emf) => {
+                crate::images::emf::convert_emf_to_png(preview, width, height)?
+            },
+            Some(PreviewFormat::Wmf) => {
+                crate::images::wmf::convert_wmf_to_png(preview, width, height)?
+            },
+            Some(PreviewFormat::Png) => preview.to_vec(),
+            None => {
+                return Err(Error::UnsupportedFeature(
+                    "unrecognized preview image format",
+                ));
+            },
+        };
+        Ok(Some(png))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_preview_format() {
+        let mut emf = vec![0x01, 0x00, 0x00, 0x00];
+        emf.resize(40, 0);
+        emf.extend_from_slice(b" EMF");
+        assert_eq!(PreviewFormat::detect(&emf), Some(PreviewFormat::Emf));
+        assert_eq!(
+            PreviewFormat::detect(&[0xD7, 0xCD, 0xC6, 0x9A, 0x00, 0x00]),
+            Some(PreviewFormat::Wmf)
+        );
+        assert_eq!(
+            PreviewFormat::detect(&[0x01, 0x00, 0x09, 0x00, 0x00, 0x03]),
+            Some(PreviewFormat::Wmf)
+        );
+        assert_eq!(
+            PreviewFormat::detect(b"\x89PNG\r\n\x1a\n...."),
+            Some(PreviewFormat::Png)
+        );
+        assert_eq!(PreviewFormat::detect(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn test_package_payload() {
+        let object = EmbeddedObject::new(
+            Some("Excel.Sheet.12".to_string()),
+            b"PK\x03\x04rest".to_vec(),
+            None,
+        );
+        assert_eq!(object.prog_id(), Some("Excel.Sheet.12"));
+        assert_eq!(object.payload(), Some(&b"PK\x03\x04rest"[..]));
+        assert!(object.clsid().is_none());
+    }
+}
@@ -0,0 +1,74 @@
+//! Text box and shape text implementation for Word documents.
+
+use super::Paragraph;
+use super::paragraph::join_paragraph_runs;
+use crate::common::Result;
+
+/// Text of a text box or shape, anchored to a body paragraph.
+///
+/// Floating text is not part of [`Document::text`](super::Document::text);
+/// use [`TextOptions::with_floating_text`](super::TextOptions::with_floating_text)
+/// to include it.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for text_box in doc.floating_text()? {
+///     println!(
+///         "paragraph {}: {}",
+///         text_box.paragraph_index(),
+///         text_box.text()?
+///     );
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct FloatingText {
+    paragraph_index: usize,
+    name: Option<String>,
+    paragraphs: Vec<Paragraph>,
+}
+
+impl FloatingText {
+    #[cfg(any(feature = "ole", feature = "ooxml"))]
+    pub(crate) fn new(
+        paragraph_index: usize,
+        name: Option<String>,
+        paragraphs: Vec<Paragraph>,
+    ) -> Self {
+        Self {
+            paragraph_index,
+            name,
+            paragraphs,
+        }
+    }
+
+    /// Index into [`Document::paragraphs`](super::Document::paragraphs) of the
+    /// paragraph the shape is anchored to.
+    #[inline]
+    pub fn paragraph_index(&self) -> usize {
+        self.paragraph_index
+    }
+
+    /// The shape name, such as "Text Box 1" (`wp:docPr/@name`, .docx only).
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The paragraphs making up the shape text.
+    #[inline]
+    pub fn paragraphs(&self) -> &[Paragraph] {
+        &self.paragraphs
+    }
+
+    /// The shape text, with paragraphs separated by newlines.
+    pub fn text(&self) -> Result<String> {
+        Ok(join_paragraph_runs(&self.paragraphs)?
+            .trim_end_matches(|c: char| c.is_whitespace() || c.is_control())
+            .to_string())
+    }
+}
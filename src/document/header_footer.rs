@@ -0,0 +1,80 @@
+//! Header and footer implementation for Word documents.
+
+use super::Paragraph;
+use super::paragraph::join_paragraph_runs;
+use crate::common::Result;
+
+/// Which pages of a section a header or footer is printed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeaderFooterType {
+    /// Printed on all pages, or on odd pages when an even header/footer exists
+    Default,
+    /// Printed on the first page of the section
+    First,
+    /// Printed on even pages
+    Even,
+}
+
+/// A header or footer of a document section.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for header in doc.headers()? {
+///     println!(
+///         "Section {} {:?} header: {}",
+///         header.section_index(),
+///         header.header_footer_type(),
+///         header.text()?
+///     );
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct HeaderFooter {
+    section_index: usize,
+    header_footer_type: HeaderFooterType,
+    paragraphs: Vec<Paragraph>,
+}
+
+impl HeaderFooter {
+    pub(crate) fn new(
+        section_index: usize,
+        header_footer_type: HeaderFooterType,
+        paragraphs: Vec<Paragraph>,
+    ) -> Self {
+        Self {
+            section_index,
+            header_footer_type,
+            paragraphs,
+        }
+    }
+
+    /// Index of the section this header or footer belongs to (0-based).
+    #[inline]
+    pub fn section_index(&self) -> usize {
+        self.section_index
+    }
+
+    /// Which pages of the section this header or footer is printed on.
+    #[inline]
+    pub fn header_footer_type(&self) -> HeaderFooterType {
+        self.header_footer_type
+    }
+
+    /// The paragraphs making up the header or footer content.
+    #[inline]
+    pub fn paragraphs(&self) -> &[Paragraph] {
+        &self.paragraphs
+    }
+
+    /// The header or footer text, with paragraphs separated by newlines.
+    pub fn text(&self) -> Result<String> {
+        Ok(join_paragraph_runs(&self.paragraphs)?
+            .trim_matches(|c: char| c.is_whitespace() || c.is_control())
+            .to_string())
+    }
+}
@@ -0,0 +1,150 @@
+//! Image reference implementation for Word documents.
+
+/// A picture in the document body, with its position and alt text.
+///
+/// Image references carry metadata only; the picture bytes are read with
+/// [`Document::image_data`](super::Document::image_data).
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for image in doc.images()? {
+///     println!(
+///         "paragraph {}: {} ({}x{} EMU)",
+///         image.paragraph_index(),
+///         image.alt_text().unwrap_or("no alt text"),
+///         image.width_emu(),
+///         image.height_emu()
+///     );
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImageRef {
+    pub(crate) paragraph_index: usize,
+    pub(crate) description: Option<String>,
+    pub(crate) title: Option<String>,
+    pub(crate) name: Option<String>,
+    pub(crate) width_emu: u64,
+    pub(crate) height_emu: u64,
+    pub(crate) relationship_id: Option<String>,
+    pub(crate) blip_index: Option<u32>,
+    pub(crate) extension: Option<&'static str>,
+    /// Offset of the picture in the .doc Data stream
+    pub(crate) pic_offset: Option<u32>,
+}
+
+impl ImageRef {
+    /// Index into [`Document::paragraphs`](super::Document::paragraphs) of the
+    /// paragraph the picture is anchored to.
+    #[inline]
+    pub fn paragraph_index(&self) -> usize {
+        self.paragraph_index
+    }
+
+    /// The alt text description (`wp:docPr/@descr` for .docx, the
+    /// `wzDescription` shape property for .doc).
+    #[inline]
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// The alt text title (`wp:docPr/@title`, .docx only).
+    #[inline]
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// The alt text to show for the picture: the description, or the title
+    /// when there is no description.
+    #[inline]
+    pub fn alt_text(&self) -> Option<&str> {
+        self.description().or_else(|| self.title())
+    }
+
+    /// The shape name, such as "Picture 1".
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The displayed width in EMU (914400 per inch).
+    #[inline]
+    pub fn width_emu(&self) -> u64 {
+        self.width_emu
+    }
+
+    /// The displayed height in EMU (914400 per inch).
+    #[inline]
+    pub fn height_emu(&self) -> u64 {
+        self.height_emu
+    }
+
+    /// The relationship id of the image part (.docx only).
+    #[inline]
+    pub fn relationship_id(&self) -> Option<&str> {
+        self.relationship_id.as_deref()
+    }
+
+    /// The 1-based index of the picture's BSE in the BLIP store (.doc only).
+    #[inline]
+    pub fn blip_index(&self) -> Option<u32> {
+        self.blip_index
+    }
+
+    /// The file extension of the stored picture, such as `png` or `emf`.
+    #[inline]
+    pub fn extension(&self) -> Option<&'static str> {
+        self.extension
+    }
+}
+
+/// Convert metafiles and raw DIBs to PNG so that Markdown viewers and
+/// browsers can show them, keeping the original data if conversion fails.
+#[cfg(feature = "imgconv")]
+pub(crate) fn displayable(data: Vec<u8>, extension: &'static str) -> (Vec<u8>, &'static str) {
+    use crate::images::{dib, emf, pict, wmf};
+
+    let png = match extension {
+        "emf" => emf::convert_emf_to_png(&data, None, None),
+        "wmf" => wmf::convert_wmf_to_png(&data, None, None),
+        "pict" => pict::convert_pict_to_png(&data, None, None),
+        "dib" => dib::Dib::parse(&data).and_then(|dib| dib::encode_png(dib.image())),
+        _ => return (data, extension),
+    };
+    match png {
+        Ok(png) => (png, "png"),
+        Err(_) => (data, extension),
+    }
+}
+
+/// Without `imgconv`, pictures are copied in their original format.
+#[cfg(not(feature = "imgconv"))]
+pub(crate) fn displayable(data: Vec<u8>, extension: &'static str) -> (Vec<u8>, &'static str) {
+    (data, extension)
+}
+
+#[cfg(all(test, feature = "imgconv"))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_displayable_converts_metafiles() {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/images/emf/wrench.emf");
+        let (png, extension) = displayable(std::fs::read(path).unwrap(), "emf");
+        assert_eq!(extension, "png");
+        assert!(png.starts_with(b"\x89PNG"));
+
+        let (data, extension) = displayable(b"GIF89a".to_vec(), "gif");
+        assert_eq!((data.as_slice(), extension), (&b"GIF89a"[..], "gif"));
+
+        // Unreadable metafiles are kept as they are
+        let (data, extension) = displayable(vec![0; 8], "wmf");
+        assert_eq!((data, extension), (vec![0; 8], "wmf"));
+    }
+}
@@ -0,0 +1,282 @@
+//! List (numbering) implementation for Word documents.
+
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+use std::collections::HashMap;
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+use std::hash::Hash;
+
+/// Numbering of a paragraph that is a list item.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for para in doc.paragraphs()? {
+///     if let Some(list) = para.list_info() {
+///         let indent = "  ".repeat(list.level() as usize);
+///         println!("{}{} {}", indent, list.number_text(), para.text()?);
+///     }
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ListInfo {
+    level: u8,
+    is_ordered: bool,
+    number_text: String,
+}
+
+impl ListInfo {
+    pub(crate) fn new(level: u8, is_ordered: bool, number_text: String) -> Self {
+        Self {
+            level,
+            is_ordered,
+            number_text,
+        }
+    }
+
+    /// The nesting level, 0 for top-level items.
+    #[inline]
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Whether the list is numbered rather than bulleted.
+    #[inline]
+    pub fn is_ordered(&self) -> bool {
+        self.is_ordered
+    }
+
+    /// The label Word displays before the item, such as `"1."`, `"a)"`,
+    /// `"ii."` or `"•"`.
+    #[inline]
+    pub fn number_text(&self) -> &str {
+        &self.number_text
+    }
+}
+
+/// How the counter of a list level is written.
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberStyle {
+    /// 1, 2, 3
+    Decimal,
+    /// a, b, c
+    LowerLetter,
+    /// A, B, C
+    UpperLetter,
+    /// i, ii, iii
+    LowerRoman,
+    /// I, II, III
+    UpperRoman,
+    /// A bullet symbol instead of a counter
+    Bullet,
+    /// No label at all
+    None,
+}
+
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+impl NumberStyle {
+    /// Write `value` in this style.
+    fn format(self, value: u32) -> String {
+        match self {
+            Self::Decimal => value.to_string(),
+            Self::LowerLetter => letters(value),
+            Self::UpperLetter => letters(value).to_uppercase(),
+            Self::LowerRoman => roman(value).to_lowercase(),
+            Self::UpperRoman => roman(value),
+            Self::Bullet | Self::None => String::new(),
+        }
+    }
+}
+
+/// Letter counter as Word writes it: a-z, then aa-zz, and so on.
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+fn letters(value: u32) -> String {
+    let Some(index) = value.checked_sub(1) else {
+        return String::new();
+    };
+    let letter = (b'a' + (index % 26) as u8) as char;
+    std::iter::repeat_n(letter, (index / 26 + 1) as usize).collect()
+}
+
+/// Uppercase Roman numeral; empty for zero.
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+fn roman(mut value: u32) -> String {
+    const NUMERALS: [(u32, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut result = String::new();
+    for (amount, numeral) in NUMERALS {
+        while value >= amount {
+            result.push_str(numeral);
+            value -= amount;
+        }
+    }
+    result
+}
+
+/// Format of one list level.
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+#[derive(Debug, Clone)]
+pub(crate) struct ListLevelFormat {
+    /// Value of the first item
+    pub start: u32,
+    /// How the counter is written
+    pub style: NumberStyle,
+    /// Label template where `%1`-`%9` stand for the counters of levels 0-8
+    pub text: String,
+}
+
+/// Counts list items in document order to compute their labels.
+///
+/// Each list is identified by a key; items of the same list continue its
+/// numbering, and an item restarts the numbering of every deeper level.
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+#[derive(Debug)]
+pub(crate) struct ListCounter<K> {
+    counters: HashMap<K, [Option<u32>; 9]>,
+}
+
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+impl<K: Hash + Eq> ListCounter<K> {
+    pub(crate) fn new() -> Self {
+        Self {
+            counters: HashMap::new(),
+        }
+    }
+
+    /// Count the next item of list `key` at `level`, given the formats of
+    /// all levels of that list.
+    pub(crate) fn next(
+        &mut self,
+        key: K,
+        level: u8,
+        levels: &[ListLevelFormat],
+    ) -> Option<ListInfo> {
+        let format = levels.get(level as usize)?;
+        let index = (level as usize).min(8);
+        let counters = self.counters.entry(key).or_insert([None; 9]);
+        counters[index] = Some(counters[index].map_or(format.start, |value| value + 1));
+        for deeper in &mut counters[index + 1..] {
+            *deeper = None;
+        }
+
+        let number_text = match format.style {
+            NumberStyle::Bullet => bullet_text(&format.text),
+            _ => {
+                let mut text = String::with_capacity(format.text.len() + 4);
+                let mut chars = format.text.chars().peekable();
+                while let Some(c) = chars.next() {
+                    let placeholder = chars
+                        .peek()
+                        .and_then(|next| next.to_digit(10))
+                        .filter(|digit| c == '%' && (1..=9).contains(digit));
+                    match placeholder {
+                        Some(digit) => {
+                            chars.next();
+                            let placeholder_level = digit as usize - 1;
+                            if let Some(level_format) = levels.get(placeholder_level) {
+                                let value =
+                                    counters[placeholder_level].unwrap_or(level_format.start);
+                                text.push_str(&level_format.style.format(value));
+                            }
+                        },
+                        None => text.push(c),
+                    }
+                }
+                text
+            },
+        };
+
+        let is_ordered = !matches!(format.style, NumberStyle::Bullet | NumberStyle::None);
+        Some(ListInfo::new(index as u8, is_ordered, number_text))
+    }
+}
+
+/// Normalize a bullet label, mapping symbol-font glyphs to a plain bullet.
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+fn bullet_text(text: &str) -> String {
+    // Symbol and Wingdings bullets live in the private use area (U+F000-U+F0FF)
+    if text.is_empty() || text.chars().any(|c| ('\u{F000}'..='\u{F0FF}').contains(&c)) {
+        "•".to_string()
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(all(test, any(feature = "ole", feature = "ooxml")))]
+mod tests {
+    use super::*;
+
+    fn level(start: u32, style: NumberStyle, text: &str) -> ListLevelFormat {
+        ListLevelFormat {
+            start,
+            style,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_number_styles() {
+        assert_eq!(NumberStyle::Decimal.format(12), "12");
+        assert_eq!(NumberStyle::LowerLetter.format(1), "a");
+        assert_eq!(NumberStyle::LowerLetter.format(28), "bb");
+        assert_eq!(NumberStyle::UpperLetter.format(26), "Z");
+        assert_eq!(NumberStyle::LowerRoman.format(4), "iv");
+        assert_eq!(NumberStyle::UpperRoman.format(1994), "MCMXCIV");
+        assert_eq!(NumberStyle::UpperRoman.format(0), "");
+    }
+
+    #[test]
+    fn test_list_counter() {
+        let levels = [
+            level(1, NumberStyle::Decimal, "%1."),
+            level(1, NumberStyle::LowerLetter, "%2)"),
+            level(1, NumberStyle::LowerRoman, "%1.%2.%3"),
+        ];
+        let mut counter = ListCounter::new();
+        let mut next = |key: u32, lvl: u8| counter.next(key, lvl, &levels).unwrap();
+
+        assert_eq!(next(1, 0), ListInfo::new(0, true, "1.".to_string()));
+        assert_eq!(next(1, 1).number_text(), "a)");
+        assert_eq!(next(1, 1).number_text(), "b)");
+        assert_eq!(next(1, 2).number_text(), "1.b.i");
+        assert_eq!(next(1, 0).number_text(), "2.");
+        // Deeper levels restart after a shallower item
+        assert_eq!(next(1, 1).number_text(), "a)");
+        // Other lists count separately
+        assert_eq!(next(2, 0).number_text(), "1.");
+        assert_eq!(next(1, 0).number_text(), "3.");
+    }
+
+    #[test]
+    fn test_list_counter_bullets() {
+        let levels = [
+            level(1, NumberStyle::Bullet, "\u{F0B7}"),
+            level(1, NumberStyle::Bullet, "o"),
+        ];
+        let mut counter = ListCounter::new();
+
+        let item = counter.next(0u32, 0, &levels).unwrap();
+        assert!(!item.is_ordered());
+        assert_eq!(item.number_text(), "•");
+        assert_eq!(counter.next(0, 1, &levels).unwrap().number_text(), "o");
+        assert!(counter.next(0, 5, &levels).is_none());
+    }
+}
@@ -9,8 +9,20 @@
 //! The module provides a format-agnostic API following the python-docx design:
 //! - `Document`: The main document API (auto-detects format)
 //! - `Paragraph`: Paragraph with text runs
+//! - `Direction`: Left-to-right or right-to-left base direction of a paragraph
+//! - `ListInfo`: Numbering of a paragraph that is a list item
 //! - `Run`: Text run with formatting
 //! - `Table`: Table with rows and cells
+//! - `Note`: Footnote or endnote with its content paragraphs
+//! - `HeaderFooter`: Section header or footer with its content paragraphs
+//! - `EmbeddedObject`: Embedded OLE object with its payload and preview image
+//! - `ImageRef`: Picture with its paragraph, alt text and size
+//! - `FloatingText`: Text box or shape text with the paragraph it is anchored to
+//! - `OutlineEntry`: Heading with its level, for tables of contents
+//! - `Revision`: Tracked change with its author, date and affected text
+//! - `Section`: Page size, orientation and margins of a section
+//! - `DocumentStatistics`: Word, character, paragraph and page counts
+//! - `DocumentDiff`: Changes between two documents, as computed by [`diff`]
 //!
 //! # Example
 //!
@@ -46,16 +58,48 @@
 //! ```
 
 // Submodule declarations
+mod diff;
 mod doc;
 mod element;
+mod embedded_object;
+mod floating_text;
+mod header_footer;
+mod image;
+mod list;
+mod note;
+mod outline;
 mod paragraph;
+mod revision;
 mod run;
+mod section;
+mod statistics;
 mod table;
+mod text_options;
 mod types;
 
 // Re-exports
+pub use diff::{
+    CellChange, ChangeKind, DiffOptions, DiffSegment, DocumentDiff, ParagraphChange, diff,
+};
 pub use doc::Document;
 pub use element::DocumentElement;
-pub use paragraph::Paragraph;
+pub use embedded_object::{EmbeddedObject, PreviewFormat};
+pub use floating_text::FloatingText;
+pub use header_footer::{HeaderFooter, HeaderFooterType};
+pub use image::ImageRef;
+pub(crate) use image::displayable;
+pub use list::ListInfo;
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+pub(crate) use list::{ListCounter, ListLevelFormat, NumberStyle};
+pub use note::{Note, NoteKind};
+pub use outline::OutlineEntry;
+pub use paragraph::{Direction, Paragraph};
+pub use revision::{Revision, RevisionMode, RevisionType};
 pub use run::Run;
-pub use table::{Cell, Row, Table};
+pub use section::{PageMargins, PageOrientation, Section};
+pub use statistics::DocumentStatistics;
+pub use table::{
+    BorderLineStyle, Cell, CellBorder, CellBorders, CellPlacement, CellVerticalAlignment, Row,
+    Table,
+};
+pub use text_options::TextOptions;
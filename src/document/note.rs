@@ -0,0 +1,92 @@
+//! Footnote and endnote implementation for Word documents.
+
+use super::Paragraph;
+use super::paragraph::join_paragraph_runs;
+use crate::common::Result;
+
+/// Whether a note is printed at the bottom of the page or at the end of the document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteKind {
+    /// Footnote
+    Footnote,
+    /// Endnote
+    Endnote,
+}
+
+/// A footnote or endnote in a Word document.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for note in doc.footnotes()? {
+///     println!(
+///         "[^{}] in paragraph {:?}: {}",
+///         note.id(),
+///         note.paragraph_index(),
+///         note.text()?
+///     );
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Note {
+    kind: NoteKind,
+    id: u32,
+    paragraph_index: Option<usize>,
+    paragraphs: Vec<Paragraph>,
+}
+
+impl Note {
+    pub(crate) fn new(
+        kind: NoteKind,
+        id: u32,
+        paragraph_index: Option<usize>,
+        paragraphs: Vec<Paragraph>,
+    ) -> Self {
+        Self {
+            kind,
+            id,
+            paragraph_index,
+            paragraphs,
+        }
+    }
+
+    /// Whether this is a footnote or an endnote.
+    #[inline]
+    pub fn kind(&self) -> NoteKind {
+        self.kind
+    }
+
+    /// The note number, unique among notes of the same kind.
+    ///
+    /// For .docx this is the `w:id` of the note; for .doc it is the 1-based
+    /// position of the note in the document.
+    #[inline]
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Index into [`Document::paragraphs`](super::Document::paragraphs) of the
+    /// paragraph holding the reference mark, if it could be located.
+    #[inline]
+    pub fn paragraph_index(&self) -> Option<usize> {
+        self.paragraph_index
+    }
+
+    /// The paragraphs making up the note content.
+    #[inline]
+    pub fn paragraphs(&self) -> &[Paragraph] {
+        &self.paragraphs
+    }
+
+    /// The note text, with paragraphs separated by newlines.
+    pub fn text(&self) -> Result<String> {
+        // Drop the leading reference mark (an auto-number character in .doc files)
+        Ok(join_paragraph_runs(&self.paragraphs)?
+            .trim_matches(|c: char| c.is_whitespace() || c.is_control())
+            .to_string())
+    }
+}
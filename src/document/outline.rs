@@ -0,0 +1,55 @@
+//! Heading outline implementation for Word documents.
+
+/// A heading in the document outline.
+///
+/// Headings are recognized by the outline level of their paragraph or its
+/// style, not by the style name, so localized style names such as
+/// "Überschrift 1" are handled.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for entry in doc.outline()? {
+///     let indent = "  ".repeat(usize::from(entry.level() - 1));
+///     println!("{}{} (paragraph {})", indent, entry.text(), entry.paragraph_index());
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutlineEntry {
+    level: u8,
+    text: String,
+    paragraph_index: usize,
+}
+
+impl OutlineEntry {
+    pub(crate) fn new(level: u8, text: String, paragraph_index: usize) -> Self {
+        Self {
+            level,
+            text,
+            paragraph_index,
+        }
+    }
+
+    /// The heading level, from 1 (top level) to 9.
+    #[inline]
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// The heading text.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Index into [`Document::paragraphs`](super::Document::paragraphs) of the
+    /// heading paragraph.
+    #[inline]
+    pub fn paragraph_index(&self) -> usize {
+        self.paragraph_index
+    }
+}
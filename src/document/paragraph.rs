@@ -1,7 +1,8 @@
 //! Paragraph implementation for Word documents.
 
-use super::Run;
+use super::{ListInfo, Run};
 #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf"))]
+#[cfg(any(feature = "ole", feature = "ooxml"))]
 use crate::common::Error;
 use crate::common::Result;
 
@@ -11,6 +12,25 @@ use crate::ole;
 #[cfg(feature = "ooxml")]
 use crate::ooxml;
 
+/// The base direction of a paragraph.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Direction {
+    /// Left-to-right, as for Latin, CJK and most other scripts
+    #[default]
+    LeftToRight,
+    /// Right-to-left, as for Arabic and Hebrew
+    RightToLeft,
+}
+
+impl Direction {
+    /// Check whether the direction is right-to-left.
+    #[inline]
+    pub fn is_rtl(self) -> bool {
+        self == Direction::RightToLeft
+    }
+}
+
 /// A paragraph in a Word document.
 #[derive(Debug, Clone)]
 pub enum Paragraph {
@@ -19,7 +39,7 @@ pub enum Paragraph {
     #[cfg(feature = "ooxml")]
     Docx(ooxml::docx::Paragraph),
     #[cfg(feature = "iwa")]
-    Pages(String),
+    Pages(crate::iwa::text::StyledParagraph),
     #[cfg(feature = "rtf")]
     Rtf(crate::rtf::ParagraphContent<'static>),
     #[cfg(feature = "odf")]
@@ -35,13 +55,11 @@ impl Paragraph {
             #[cfg(feature = "ooxml")]
             Paragraph::Docx(p) => p.text().map(|s| s.to_string()).map_err(Error::from),
             #[cfg(feature = "iwa")]
-            Paragraph::Pages(text) => Ok(text.clone()),
+            Paragraph::Pages(p) => Ok(p.text()),
             #[cfg(feature = "rtf")]
             Paragraph::Rtf(p) => Ok(p.text()),
             #[cfg(feature = "odf")]
-            Paragraph::Odt(p) => p
-                .text()
-                .map_err(|e| Error::ParseError(format!("Failed to get paragraph text: {}", e))),
+            Paragraph::Odt(p) => p.text(),
         }
     }
 
@@ -59,22 +77,86 @@ impl Paragraph {
                 Ok(runs.into_iter().map(Run::Docx).collect())
             },
             #[cfg(feature = "iwa")]
-            Paragraph::Pages(text) => {
-                // Pages paragraphs are simple strings without run-level formatting
-                // Return a single run with the entire text
-                Ok(vec![Run::Pages(text.clone())])
-            },
+            Paragraph::Pages(p) => Ok(p.runs.iter().cloned().map(Run::Pages).collect()),
             #[cfg(feature = "rtf")]
             Paragraph::Rtf(p) => Ok(p.runs().iter().map(|r| Run::Rtf(r.clone())).collect()),
             #[cfg(feature = "odf")]
             Paragraph::Odt(p) => {
-                let runs = p
-                    .runs()
-                    .map_err(|e| Error::ParseError(format!("Failed to get runs: {}", e)))?;
+                let runs = p.runs()?;
                 Ok(runs.into_iter().map(Run::Odt).collect())
             },
         }
     }
+
+    /// Get the base direction of the paragraph.
+    ///
+    /// Read from the paragraph's own properties (`w:bidi`, sprmPFBiDi,
+    /// `\rtlpar`); a direction set only by the paragraph style is not
+    /// seen. ODF and Pages paragraphs are always left-to-right.
+    pub fn direction(&self) -> Result<Direction> {
+        let rtl = match self {
+            #[cfg(feature = "ole")]
+            Paragraph::Doc(p) => p.properties().bi_directional,
+            #[cfg(feature = "ooxml")]
+            Paragraph::Docx(p) => p.bidi().map_err(Error::from)?.unwrap_or(false),
+            #[cfg(feature = "rtf")]
+            Paragraph::Rtf(p) => p.properties.rtl,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        };
+        Ok(if rtl {
+            Direction::RightToLeft
+        } else {
+            Direction::LeftToRight
+        })
+    }
+
+    /// Get the numbering of this paragraph if it is a list item.
+    ///
+    /// Labels are computed by counting the preceding items of each list, so
+    /// they are set on the body paragraphs returned by
+    /// [`Document::paragraphs`](super::Document::paragraphs) and
+    /// [`Document::elements`](super::Document::elements) of .doc and .docx
+    /// files. Other paragraphs return `None`.
+    pub fn list_info(&self) -> Option<&ListInfo> {
+        match self {
+            #[cfg(feature = "ole")]
+            Paragraph::Doc(p) => p.list_info(),
+            #[cfg(feature = "ooxml")]
+            Paragraph::Docx(p) => p.list_info(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        }
+    }
+}
+
+/// Text of `paragraphs` joined with newlines.
+///
+/// Joins run texts rather than paragraph texts, since .doc paragraphs keep
+/// their text in the runs.
+pub(super) fn join_paragraph_runs(paragraphs: &[Paragraph]) -> Result<String> {
+    let texts = paragraphs
+        .iter()
+        .map(|para| para.runs()?.iter().map(|run| run.text()).collect())
+        .collect::<Result<Vec<String>>>()?;
+    Ok(texts.join("\n"))
+}
+
+/// Serializes the paragraph's text, list numbering and runs.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Paragraph {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        let mut state = serializer.serialize_struct("Paragraph", 3)?;
+        state.serialize_field("text", &self.text().map_err(S::Error::custom)?)?;
+        state.serialize_field("list", &self.list_info())?;
+        state.serialize_field("runs", &self.runs().map_err(S::Error::custom)?)?;
+        state.end()
+    }
 }
 
 #[cfg(test)]
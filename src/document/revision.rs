@@ -0,0 +1,119 @@
+//! Tracked change (revision) implementation for Word documents.
+
+use std::fmt;
+
+/// Kind of change recorded by a tracked revision.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RevisionType {
+    /// Inserted content
+    Insert,
+    /// Deleted content
+    Delete,
+    /// Source of moved content
+    MoveFrom,
+    /// Destination of moved content
+    MoveTo,
+    /// Formatting change
+    FormatChange,
+    /// Any other kind of change
+    Other,
+}
+
+impl fmt::Display for RevisionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Insert => write!(f, "Insert"),
+            Self::Delete => write!(f, "Delete"),
+            Self::MoveFrom => write!(f, "Move From"),
+            Self::MoveTo => write!(f, "Move To"),
+            Self::FormatChange => write!(f, "Format Change"),
+            Self::Other => write!(f, "Other"),
+        }
+    }
+}
+
+/// How tracked changes are materialized by
+/// [`Document::text_with_revisions`](super::Document::text_with_revisions).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RevisionMode {
+    /// Text as it reads once every change is accepted
+    #[default]
+    AcceptAll,
+    /// Text as it read before any change was made
+    RejectAll,
+    /// Both versions, with insertions written as `{++text++}` and deletions
+    /// as `{--text--}` (CriticMarkup)
+    ShowMarkup,
+}
+
+/// A tracked change in a Word document.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for revision in doc.revisions()? {
+///     println!(
+///         "{} by {:?}: {}",
+///         revision.revision_type(),
+///         revision.author(),
+///         revision.text()
+///     );
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Revision {
+    author: Option<String>,
+    date: Option<String>,
+    text: String,
+    revision_type: RevisionType,
+}
+
+impl Revision {
+    pub(crate) fn new(
+        revision_type: RevisionType,
+        author: Option<String>,
+        date: Option<String>,
+        text: String,
+    ) -> Self {
+        Self {
+            author,
+            date,
+            text,
+            revision_type,
+        }
+    }
+
+    /// Append text to a revision spanning several runs.
+    #[cfg(feature = "ole")]
+    pub(crate) fn push_text(&mut self, text: &str) {
+        self.text.push_str(text);
+    }
+
+    /// The kind of change.
+    #[inline]
+    pub fn revision_type(&self) -> RevisionType {
+        self.revision_type
+    }
+
+    /// The author of the change, if recorded.
+    #[inline]
+    pub fn author(&self) -> Option<&str> {
+        self.author.as_deref()
+    }
+
+    /// The date and time of the change (ISO 8601), if recorded.
+    #[inline]
+    pub fn date(&self) -> Option<&str> {
+        self.date.as_deref()
+    }
+
+    /// The text affected by the change.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
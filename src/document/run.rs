@@ -1,8 +1,9 @@
 //! Text run implementation for Word documents.
 
 #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf"))]
+#[cfg(any(feature = "ole", feature = "ooxml"))]
 use crate::common::Error;
-use crate::common::Result;
+use crate::common::{Language, Result};
 
 #[cfg(feature = "ole")]
 use crate::ole;
@@ -18,7 +19,7 @@ pub enum Run {
     #[cfg(feature = "ooxml")]
     Docx(ooxml::docx::Run),
     #[cfg(feature = "iwa")]
-    Pages(String),
+    Pages(crate::iwa::text::StyledRun),
     #[cfg(feature = "rtf")]
     Rtf(crate::rtf::Run<'static>),
     #[cfg(feature = "odf")]
@@ -34,13 +35,11 @@ impl Run {
             #[cfg(feature = "ooxml")]
             Run::Docx(r) => r.text().map(|s| s.to_string()).map_err(Error::from),
             #[cfg(feature = "iwa")]
-            Run::Pages(text) => Ok(text.clone()),
+            Run::Pages(r) => Ok(r.text.clone()),
             #[cfg(feature = "rtf")]
             Run::Rtf(r) => Ok(r.text().to_string()),
             #[cfg(feature = "odf")]
-            Run::Odt(r) => r
-                .text()
-                .map_err(|e| Error::ParseError(format!("Failed to get run text: {}", e))),
+            Run::Odt(r) => r.text(),
         }
     }
 
@@ -52,7 +51,7 @@ impl Run {
             #[cfg(feature = "ooxml")]
             Run::Docx(r) => r.bold().map_err(Error::from),
             #[cfg(feature = "iwa")]
-            Run::Pages(_) => Ok(None), // Pages doesn't support run-level formatting in the current API
+            Run::Pages(r) => Ok(Some(r.style.bold)),
             #[cfg(feature = "rtf")]
             Run::Rtf(r) => Ok(r.bold()),
             #[cfg(feature = "odf")]
@@ -68,7 +67,7 @@ impl Run {
             #[cfg(feature = "ooxml")]
             Run::Docx(r) => r.italic().map_err(Error::from),
             #[cfg(feature = "iwa")]
-            Run::Pages(_) => Ok(None), // Pages doesn't support run-level formatting in the current API
+            Run::Pages(r) => Ok(Some(r.style.italic)),
             #[cfg(feature = "rtf")]
             Run::Rtf(r) => Ok(r.italic()),
             #[cfg(feature = "odf")]
@@ -84,7 +83,7 @@ impl Run {
             #[cfg(feature = "ooxml")]
             Run::Docx(r) => r.strikethrough().map_err(Error::from),
             #[cfg(feature = "iwa")]
-            Run::Pages(_) => Ok(None), // Pages doesn't support run-level formatting in the current API
+            Run::Pages(r) => Ok(Some(r.style.strikethrough)),
             #[cfg(feature = "rtf")]
             Run::Rtf(r) => Ok(r.strikethrough()),
             #[cfg(feature = "odf")]
@@ -92,6 +91,88 @@ impl Run {
         }
     }
 
+    /// Get the font size of the run in points.
+    ///
+    /// Returns the size set on the run itself, or `None` if it is inherited
+    /// from styles. Sizes of .odt runs, which always come from styles, are
+    /// not resolved.
+    pub fn font_size(&self) -> Result<Option<f32>> {
+        match self {
+            #[cfg(feature = "ole")]
+            Run::Doc(r) => Ok(r.font_size().map(|half_points| half_points as f32 / 2.0)),
+            #[cfg(feature = "ooxml")]
+            Run::Docx(r) => Ok(r
+                .font_size()
+                .map_err(Error::from)?
+                .map(|half_points| half_points as f32 / 2.0)),
+            #[cfg(feature = "iwa")]
+            Run::Pages(r) => Ok(r.style.font_size),
+            #[cfg(feature = "rtf")]
+            Run::Rtf(r) => Ok(Some(r.formatting.font_size.get() as f32 / 2.0)),
+            #[cfg(feature = "odf")]
+            Run::Odt(_) => Ok(None),
+        }
+    }
+
+    /// Get the declared languages of the run as BCP-47 tags, with separate
+    /// slots for Latin, East Asian and complex script text.
+    ///
+    /// Languages are resolved through the style hierarchy: the run's own
+    /// language, then its character style, then the paragraph style and the
+    /// styles it is based on, then the document defaults. Styles are only
+    /// taken into account for runs of body paragraphs returned by
+    /// [`Document::paragraphs`](super::Document::paragraphs) and
+    /// [`Document::elements`](super::Document::elements). .rtf runs fall
+    /// back to the document defaults, and .pages runs have no languages.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("document.docx")?;
+    /// for para in doc.paragraphs()? {
+    ///     for run in para.runs()? {
+    ///         let language = run.language()?;
+    ///         println!("{:?} / {:?}: {}", language.latin, language.east_asian, run.text()?);
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn language(&self) -> Result<Language> {
+        match self {
+            #[cfg(feature = "ole")]
+            Run::Doc(r) => Ok(r.language()),
+            #[cfg(feature = "ooxml")]
+            Run::Docx(r) => r.language().map_err(Error::from),
+            #[cfg(feature = "iwa")]
+            Run::Pages(_) => Ok(Language::default()),
+            #[cfg(feature = "rtf")]
+            Run::Rtf(r) => Ok(r.language()),
+            #[cfg(feature = "odf")]
+            Run::Odt(r) => Ok(r.language()),
+        }
+    }
+
+    /// Check if the run is marked as right-to-left text.
+    ///
+    /// This is the direction flag of .doc, .docx and .rtf runs (`w:rtl`,
+    /// sprmCFBiDi, `\rtlch`), which word processors set on Arabic and Hebrew
+    /// text. ODF and Pages runs have no such flag and are never marked.
+    /// The text itself is always in logical order.
+    pub fn is_rtl(&self) -> Result<bool> {
+        match self {
+            #[cfg(feature = "ole")]
+            Run::Doc(r) => Ok(r.rtl().unwrap_or(false)),
+            #[cfg(feature = "ooxml")]
+            Run::Docx(r) => Ok(r.rtl().map_err(Error::from)?.unwrap_or(false)),
+            #[cfg(feature = "rtf")]
+            Run::Rtf(r) => Ok(r.formatting.rtl),
+            #[allow(unreachable_patterns)]
+            _ => Ok(false),
+        }
+    }
+
     /// Get the vertical position of the run (superscript/subscript).
     ///
     /// Returns the vertical positioning if specified, None if normal.
@@ -120,7 +201,16 @@ impl Run {
                 }
             },
             #[cfg(feature = "iwa")]
-            Run::Pages(_) => Ok(None), // Pages doesn't support run-level formatting in the current API
+            Run::Pages(r) => {
+                let pos = if r.style.superscript {
+                    Some(VerticalPosition::Superscript)
+                } else if r.style.subscript {
+                    Some(VerticalPosition::Subscript)
+                } else {
+                    None
+                };
+                Ok(pos)
+            },
             #[cfg(feature = "rtf")]
             Run::Rtf(r) => Ok(r.vertical_position()),
             #[cfg(feature = "odf")]
@@ -129,6 +219,33 @@ impl Run {
     }
 }
 
+/// Serializes the run's text and character formatting.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Run {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        #[cfg(any(feature = "ole", feature = "ooxml", feature = "iwa"))]
+        let vertical_position = self.vertical_position().map_err(S::Error::custom)?;
+        #[cfg(not(any(feature = "ole", feature = "ooxml", feature = "iwa")))]
+        let vertical_position: Option<crate::common::VerticalPosition> = None;
+
+        let mut state = serializer.serialize_struct("Run", 5)?;
+        state.serialize_field("text", &self.text().map_err(S::Error::custom)?)?;
+        state.serialize_field("bold", &self.bold().map_err(S::Error::custom)?)?;
+        state.serialize_field("italic", &self.italic().map_err(S::Error::custom)?)?;
+        state.serialize_field(
+            "strikethrough",
+            &self.strikethrough().map_err(S::Error::custom)?,
+        )?;
+        state.serialize_field("vertical_position", &vertical_position)?;
+        state.end()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::super::Document;
@@ -0,0 +1,100 @@
+//! Section page setup for Word documents.
+
+use crate::common::Length;
+
+/// Page orientation of a section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PageOrientation {
+    /// Pages are taller than wide
+    #[default]
+    Portrait,
+    /// Pages are wider than tall
+    Landscape,
+}
+
+/// Page margins of a section.
+///
+/// Each margin is `None` when the document does not set it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PageMargins {
+    /// Top margin
+    pub top: Option<Length>,
+    /// Right margin
+    pub right: Option<Length>,
+    /// Bottom margin
+    pub bottom: Option<Length>,
+    /// Left margin
+    pub left: Option<Length>,
+    /// Distance from the top edge of the page to the header
+    pub header: Option<Length>,
+    /// Distance from the bottom edge of the page to the footer
+    pub footer: Option<Length>,
+    /// Extra margin for binding
+    pub gutter: Option<Length>,
+}
+
+/// Page setup of a document section.
+///
+/// Sizes are read from the document as stored; a page size the document
+/// leaves out is `None` rather than a Letter or A4 default.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("document.docx")?;
+/// for section in doc.sections()? {
+///     if let (Some(width), Some(height)) = (section.page_width(), section.page_height()) {
+///         println!("{:.2} x {:.2} in, {:?}", width.inches(), height.inches(), section.orientation());
+///     }
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section {
+    page_width: Option<Length>,
+    page_height: Option<Length>,
+    orientation: PageOrientation,
+    margins: PageMargins,
+}
+
+impl Section {
+    pub(crate) fn new(
+        page_width: Option<Length>,
+        page_height: Option<Length>,
+        orientation: PageOrientation,
+        margins: PageMargins,
+    ) -> Self {
+        Self {
+            page_width,
+            page_height,
+            orientation,
+            margins,
+        }
+    }
+
+    /// The page width.
+    #[inline]
+    pub fn page_width(&self) -> Option<Length> {
+        self.page_width
+    }
+
+    /// The page height.
+    #[inline]
+    pub fn page_height(&self) -> Option<Length> {
+        self.page_height
+    }
+
+    /// The page orientation, portrait unless the section says otherwise.
+    #[inline]
+    pub fn orientation(&self) -> PageOrientation {
+        self.orientation
+    }
+
+    /// The page margins.
+    #[inline]
+    pub fn margins(&self) -> &PageMargins {
+        &self.margins
+    }
+}
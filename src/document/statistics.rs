@@ -0,0 +1,97 @@
+//! Word, character and page counts for Word documents.
+
+use crate::common::Metadata;
+use crate::common::statistics::TextCounts;
+
+/// Word, character, paragraph and page counts of a document.
+///
+/// The word, character and paragraph counts are computed from the text of
+/// the body, tables included, the same way for every format; see
+/// [`count_words`](crate::common::statistics::count_words). Headers,
+/// footers, notes and text boxes are not counted.
+///
+/// The `reported_*` counts and [`page_count`](Self::page_count) are the
+/// values the application that last saved the file stored in its properties
+/// (`docProps/app.xml`, the OLE summary information stream or `meta.xml`).
+/// They are read as-is, not recomputed, so they are `None` when the file
+/// does not have them and may be stale if another tool edited the file.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+///
+/// let doc = Document::open("report.doc")?;
+/// let stats = doc.statistics()?;
+/// println!("{} words, {} characters", stats.word_count(), stats.character_count());
+/// if let Some(pages) = stats.page_count() {
+///     println!("{} pages when last saved", pages);
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentStatistics {
+    counts: TextCounts,
+    page_count: Option<u32>,
+    reported_word_count: Option<u32>,
+    reported_character_count: Option<u32>,
+}
+
+impl DocumentStatistics {
+    pub(crate) fn new(counts: TextCounts, metadata: &Metadata) -> Self {
+        Self {
+            counts,
+            page_count: metadata.page_count,
+            reported_word_count: metadata.word_count,
+            reported_character_count: metadata.character_count,
+        }
+    }
+
+    /// The number of words.
+    #[inline]
+    pub fn word_count(&self) -> usize {
+        self.counts.words
+    }
+
+    /// The number of characters, including spaces.
+    #[inline]
+    pub fn character_count(&self) -> usize {
+        self.counts.characters
+    }
+
+    /// The number of characters, not including spaces.
+    #[inline]
+    pub fn character_count_no_spaces(&self) -> usize {
+        self.counts.characters_no_spaces
+    }
+
+    /// The number of paragraphs with text. Empty paragraphs are not counted.
+    #[inline]
+    pub fn paragraph_count(&self) -> usize {
+        self.counts.paragraphs
+    }
+
+    /// The number of pages reported by the application that saved the file.
+    ///
+    /// Pages depend on layout, so they are never computed.
+    #[inline]
+    pub fn page_count(&self) -> Option<u32> {
+        self.page_count
+    }
+
+    /// The number of words reported by the application that saved the file.
+    #[inline]
+    pub fn reported_word_count(&self) -> Option<u32> {
+        self.reported_word_count
+    }
+
+    /// The number of characters reported by the application that saved the
+    /// file.
+    ///
+    /// Word stores the count without spaces, while ODF applications store
+    /// the count with spaces.
+    #[inline]
+    pub fn reported_character_count(&self) -> Option<u32> {
+        self.reported_character_count
+    }
+}
@@ -1,8 +1,10 @@
 //! Table implementation for Word documents.
 
+use super::{DocumentElement, Paragraph};
 #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf"))]
+#[cfg(any(feature = "ole", feature = "ooxml"))]
 use crate::common::Error;
-use crate::common::Result;
+use crate::common::{Length, RGBColor, Result};
 
 #[cfg(feature = "ole")]
 use crate::ole;
@@ -10,13 +12,134 @@ use crate::ole;
 #[cfg(feature = "ooxml")]
 use crate::ooxml;
 
+/// Vertical alignment of the content of a table cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CellVerticalAlignment {
+    /// Content at the top of the cell
+    #[default]
+    Top,
+    /// Content centered vertically
+    Center,
+    /// Content at the bottom of the cell
+    Bottom,
+}
+
+/// Line style of a table cell border.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BorderLineStyle {
+    /// A single line
+    Single,
+    /// A single thick line
+    Thick,
+    /// Two lines
+    Double,
+    /// Three lines
+    Triple,
+    /// A dotted line
+    Dotted,
+    /// A dashed line
+    Dashed,
+    /// Alternating dots and dashes
+    DotDash,
+    /// A dash followed by two dots
+    DotDotDash,
+    /// Any other style, such as the thin-thick line pairs
+    Other,
+}
+
+/// One edge of the border of a table cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellBorder {
+    /// Line style
+    pub style: BorderLineStyle,
+    /// Line width
+    pub width: Length,
+    /// Line color, `None` for the automatic color
+    pub color: Option<RGBColor>,
+}
+
+/// The borders a table cell sets for itself.
+///
+/// An edge is `None` when the cell has no border of its own there; the table
+/// borders may still draw one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CellBorders {
+    /// Top edge
+    pub top: Option<CellBorder>,
+    /// Left edge
+    pub left: Option<CellBorder>,
+    /// Bottom edge
+    pub bottom: Option<CellBorder>,
+    /// Right edge
+    pub right: Option<CellBorder>,
+}
+
+/// The block of the table grid a cell covers.
+///
+/// See [`Table`] for the coordinate model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CellPlacement {
+    /// Index of the first row the cell covers
+    pub row: usize,
+    /// Index of the first grid column the cell covers
+    pub column: usize,
+    /// Number of rows the cell covers
+    pub row_span: usize,
+    /// Number of grid columns the cell covers
+    pub column_span: usize,
+}
+
+/// How a physical cell takes part in merges, as the document stores it.
+#[derive(Debug, Clone, Copy)]
+struct CellMerge {
+    /// Number of grid columns the cell covers
+    column_span: usize,
+    /// Number of rows the cell covers, for formats that store row spans
+    row_span: usize,
+    /// Whether the cell continues a vertical merge from the row above
+    continues_above: bool,
+    /// Whether the cell continues a horizontal merge from its left neighbour
+    continues_left: bool,
+}
+
+impl CellMerge {
+    /// A cell that is not merged with any other.
+    const SINGLE: Self = Self {
+        column_span: 1,
+        row_span: 1,
+        continues_above: false,
+        continues_left: false,
+    };
+}
+
 /// A table in a Word document.
+///
+/// # Coordinate model
+///
+/// A table has two views of its cells:
+///
+/// - **Physical cells** are the cells as the document stores them, indexed by
+///   row and by position in the row, as returned by [`Row::cells`]. Cells that
+///   continue a vertical merge (`vMerge` in .docx, `fVertMerge` in .doc) are
+///   physical cells too, even though they show no content of their own.
+///   OpenDocument leaves such covered cells out.
+/// - **Grid positions** are the columns of the table grid. A cell covers
+///   [`Cell::grid_span`] grid columns, and the first cell of a row may start
+///   after some grid columns the row leaves out (`gridBefore`).
+///
+/// [`cell_placements`](Self::cell_placements) maps every physical cell to the
+/// block of the grid it covers, with `None` for the cells that only continue
+/// a merge, and [`column_widths`](Self::column_widths) gives the width of each
+/// grid column.
 #[derive(Debug, Clone)]
 pub enum Table {
     #[cfg(feature = "ole")]
     Doc(ole::doc::Table),
     #[cfg(feature = "ooxml")]
     Docx(Box<ooxml::docx::Table>),
+    #[cfg(feature = "iwa")]
+    Pages(Box<crate::iwa::structured::Table>),
     #[cfg(feature = "rtf")]
     Rtf(crate::rtf::Table<'static>),
     #[cfg(feature = "odf")]
@@ -24,6 +147,22 @@ pub enum Table {
 }
 
 impl Table {
+    /// Number of entries of [`Document::paragraphs`](super::Document::paragraphs)
+    /// that belong to this table.
+    ///
+    /// Only .doc and .docx list table paragraphs among the document
+    /// paragraphs; other formats report 0.
+    pub(crate) fn paragraph_count(&self) -> Result<usize> {
+        match self {
+            #[cfg(feature = "ole")]
+            Table::Doc(t) => Ok(t.paragraph_count()),
+            #[cfg(feature = "ooxml")]
+            Table::Docx(t) => t.paragraph_count().map_err(Error::from),
+            #[allow(unreachable_patterns)]
+            _ => Ok(0),
+        }
+    }
+
     /// Get the number of rows in the table.
     pub fn row_count(&self) -> Result<usize> {
         match self {
@@ -31,12 +170,12 @@ impl Table {
             Table::Doc(t) => t.row_count().map_err(Error::from),
             #[cfg(feature = "ooxml")]
             Table::Docx(t) => t.row_count().map_err(Error::from),
+            #[cfg(feature = "iwa")]
+            Table::Pages(t) => Ok(t.row_count),
             #[cfg(feature = "rtf")]
             Table::Rtf(t) => Ok(t.row_count()),
             #[cfg(feature = "odf")]
-            Table::Odt(t) => t
-                .row_count()
-                .map_err(|e| Error::ParseError(format!("Failed to get row count: {}", e))),
+            Table::Odt(t) => t.row_count(),
         }
     }
 
@@ -56,6 +195,8 @@ impl Table {
                 let rows = t.rows().map_err(Error::from)?;
                 Ok(rows.into_iter().map(|r| Row::Docx(Box::new(r))).collect())
             },
+            #[cfg(feature = "iwa")]
+            Table::Pages(t) => Ok((0..t.row_count).map(|r| pages_row(t, r)).collect()),
             #[cfg(feature = "rtf")]
             Table::Rtf(t) => {
                 let rows = t.rows();
@@ -63,9 +204,7 @@ impl Table {
             },
             #[cfg(feature = "odf")]
             Table::Odt(t) => {
-                let rows = t
-                    .rows()
-                    .map_err(|e| Error::ParseError(format!("Failed to get rows: {}", e)))?;
+                let rows = t.rows()?;
                 Ok(rows.into_iter().map(Row::Odt).collect())
             },
         }
@@ -89,6 +228,8 @@ impl Table {
                 let rows = t.rows().map_err(Error::from)?;
                 Ok(rows.get(index).cloned().map(|r| Row::Docx(Box::new(r))))
             },
+            #[cfg(feature = "iwa")]
+            Table::Pages(t) => Ok((index < t.row_count).then(|| pages_row(t, index))),
             #[cfg(feature = "rtf")]
             Table::Rtf(t) => {
                 let rows = t.rows();
@@ -96,13 +237,234 @@ impl Table {
             },
             #[cfg(feature = "odf")]
             Table::Odt(t) => {
-                let rows = t
-                    .rows()
-                    .map_err(|e| Error::ParseError(format!("Failed to get rows: {}", e)))?;
+                let rows = t.rows()?;
                 Ok(rows.get(index).cloned().map(Row::Odt))
             },
         }
     }
+
+    /// Get the width of each column of the table grid.
+    ///
+    /// Returns an empty list for formats that do not record column widths
+    /// (Pages, RTF and OpenDocument).
+    pub fn column_widths(&self) -> Result<Vec<Length>> {
+        match self {
+            #[cfg(feature = "ole")]
+            Table::Doc(t) => Ok(t.column_widths()),
+            #[cfg(feature = "ooxml")]
+            Table::Docx(t) => t.column_widths().map_err(Error::from),
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the block of the table grid each physical cell covers.
+    ///
+    /// The result has one entry per physical cell, in the order of
+    /// [`Row::cells`]. An entry is `None` when the cell only continues a merge
+    /// started by another cell, whose placement covers it. See [`Table`] for
+    /// the coordinate model.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    ///
+    /// let doc = Document::open("report.docx")?;
+    /// for table in doc.tables()? {
+    ///     for (row, cells) in table.rows()?.iter().zip(table.cell_placements()?) {
+    ///         for (cell, placement) in row.cells()?.iter().zip(cells) {
+    ///             if let Some(p) = placement {
+    ///                 println!("{} spans {}x{} at ({}, {})", cell.text()?, p.row_span, p.column_span, p.row, p.column);
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn cell_placements(&self) -> Result<Vec<Vec<Option<CellPlacement>>>> {
+        let rows = self.cell_merges()?;
+        let mut placements: Vec<Vec<Option<CellPlacement>>> = Vec::with_capacity(rows.len());
+        // The placement covering each grid column in the previous row, as
+        // (row, index in row)
+        let mut above: Vec<Option<(usize, usize)>> = Vec::new();
+        // How many more rows each grid column is covered by a stored row span
+        let mut occupied: Vec<usize> = Vec::new();
+
+        for (row_index, (grid_before, cells)) in rows.into_iter().enumerate() {
+            let mut row: Vec<Option<CellPlacement>> = Vec::with_capacity(cells.len());
+            let mut covering: Vec<Option<(usize, usize)>> = Vec::new();
+            let mut column = grid_before;
+            let mut last_placed: Option<usize> = None;
+
+            for cell in cells {
+                let span = cell.column_span.max(1);
+
+                // Legacy horizontal merges keep the merged cells in the row
+                if cell.continues_left
+                    && let Some(index) = last_placed
+                    && let Some(placement) = row[index].as_mut()
+                {
+                    placement.column_span += span;
+                    cover(&mut covering, column, span, (row_index, index));
+                    row.push(None);
+                    column += span;
+                    continue;
+                }
+
+                while occupied.get(column).is_some_and(|&rows| rows > 0) {
+                    column += 1;
+                }
+
+                if cell.continues_above
+                    && let Some((start_row, index)) = above.get(column).copied().flatten()
+                    && let Some(placement) = placements[start_row][index].as_mut()
+                {
+                    placement.row_span = row_index - start_row + 1;
+                    cover(&mut covering, column, span, (start_row, index));
+                    row.push(None);
+                    last_placed = None;
+                    column += span;
+                    continue;
+                }
+
+                cover(&mut covering, column, span, (row_index, row.len()));
+                last_placed = Some(row.len());
+                row.push(Some(CellPlacement {
+                    row: row_index,
+                    column,
+                    row_span: cell.row_span.max(1),
+                    column_span: span,
+                }));
+                column += span;
+            }
+
+            // Stored row spans cover the grid columns of the rows below
+            for rows in &mut occupied {
+                *rows = rows.saturating_sub(1);
+            }
+            for placement in row.iter().flatten() {
+                let end = placement.column + placement.column_span;
+                if occupied.len() < end {
+                    occupied.resize(end, 0);
+                }
+                for rows in &mut occupied[placement.column..end] {
+                    *rows = (*rows).max(placement.row_span - 1);
+                }
+            }
+
+            placements.push(row);
+            above = covering;
+        }
+
+        Ok(placements)
+    }
+
+    /// Read how the physical cells take part in merges.
+    ///
+    /// Each row comes with the number of grid columns it leaves out before its
+    /// first cell.
+    fn cell_merges(&self) -> Result<Vec<(usize, Vec<CellMerge>)>> {
+        match self {
+            #[cfg(feature = "ole")]
+            Table::Doc(t) => {
+                use crate::ole::doc::parts::tap::CellMergeStatus;
+
+                let rows = t.rows().map_err(Error::from)?;
+                rows.iter()
+                    .map(|row| {
+                        let cells = row.cells().map_err(Error::from)?;
+                        let merges = cells
+                            .iter()
+                            .map(|cell| {
+                                let properties = cell.properties();
+                                CellMerge {
+                                    column_span: cell.grid_span(),
+                                    row_span: 1,
+                                    continues_above: properties.is_some_and(|p| {
+                                        p.vertical_merge == CellMergeStatus::Merged
+                                    }),
+                                    continues_left: properties
+                                        .is_some_and(|p| p.merge_status == CellMergeStatus::Merged),
+                                }
+                            })
+                            .collect();
+                        Ok((row.grid_before(), merges))
+                    })
+                    .collect()
+            },
+            #[cfg(feature = "ooxml")]
+            Table::Docx(t) => {
+                let rows = t.rows().map_err(Error::from)?;
+                rows.iter()
+                    .map(|row| {
+                        let cells = row.cells().map_err(Error::from)?;
+                        let merges = cells
+                            .iter()
+                            .map(|cell| {
+                                let properties = cell.properties().map_err(Error::from)?;
+                                Ok(CellMerge {
+                                    column_span: properties.grid_span,
+                                    row_span: 1,
+                                    continues_above: properties.v_merge
+                                        == Some(ooxml::docx::VMergeState::Continue),
+                                    continues_left: false,
+                                })
+                            })
+                            .collect::<Result<_>>()?;
+                        Ok((row.grid_before().map_err(Error::from)?, merges))
+                    })
+                    .collect()
+            },
+            #[cfg(feature = "odf")]
+            Table::Odt(t) => {
+                let rows = t.rows()?;
+                rows.iter()
+                    .map(|row| {
+                        let merges = row
+                            .cells()?
+                            .iter()
+                            .map(|cell| CellMerge {
+                                column_span: cell.colspan(),
+                                row_span: cell.rowspan(),
+                                ..CellMerge::SINGLE
+                            })
+                            .collect();
+                        Ok((0, merges))
+                    })
+                    .collect()
+            },
+            #[allow(unreachable_patterns)]
+            _ => self
+                .rows()?
+                .iter()
+                .map(|row| Ok((0, vec![CellMerge::SINGLE; row.cell_count()?])))
+                .collect(),
+        }
+    }
+}
+
+/// Record that `span` grid columns from `column` are covered by a placement.
+fn cover(
+    covering: &mut Vec<Option<(usize, usize)>>,
+    column: usize,
+    span: usize,
+    placement: (usize, usize),
+) {
+    if covering.len() < column + span {
+        covering.resize(column + span, None);
+    }
+    covering[column..column + span].fill(Some(placement));
+}
+
+/// Collect the cells of a row of a Pages table.
+#[cfg(feature = "iwa")]
+fn pages_row(table: &crate::iwa::structured::Table, row: usize) -> Row {
+    Row::Pages(
+        (0..table.column_count)
+            .map(|col| table.cell(row, col))
+            .collect(),
+    )
 }
 
 /// A table row in a Word document.
@@ -112,6 +474,8 @@ pub enum Row {
     Doc(ole::doc::Row),
     #[cfg(feature = "ooxml")]
     Docx(Box<ooxml::docx::Row>),
+    #[cfg(feature = "iwa")]
+    Pages(Vec<crate::iwa::structured::CellValue>),
     #[cfg(feature = "rtf")]
     Rtf(crate::rtf::Row<'static>),
     #[cfg(feature = "odf")]
@@ -126,12 +490,12 @@ impl Row {
             Row::Doc(r) => r.cell_count().map_err(Error::from),
             #[cfg(feature = "ooxml")]
             Row::Docx(r) => r.cell_count().map_err(Error::from),
+            #[cfg(feature = "iwa")]
+            Row::Pages(cells) => Ok(cells.len()),
             #[cfg(feature = "rtf")]
             Row::Rtf(r) => Ok(r.cell_count()),
             #[cfg(feature = "odf")]
-            Row::Odt(r) => r
-                .cell_count()
-                .map_err(|e| Error::ParseError(format!("Failed to get cell count: {}", e))),
+            Row::Odt(r) => r.cell_count(),
         }
     }
 
@@ -151,6 +515,8 @@ impl Row {
                 let cells = r.cells().map_err(Error::from)?;
                 Ok(cells.into_iter().map(Cell::Docx).collect())
             },
+            #[cfg(feature = "iwa")]
+            Row::Pages(cells) => Ok(cells.iter().cloned().map(Cell::Pages).collect()),
             #[cfg(feature = "rtf")]
             Row::Rtf(r) => {
                 let cells = r.cells();
@@ -158,9 +524,7 @@ impl Row {
             },
             #[cfg(feature = "odf")]
             Row::Odt(r) => {
-                let cells = r
-                    .cells()
-                    .map_err(|e| Error::ParseError(format!("Failed to get cells: {}", e)))?;
+                let cells = r.cells()?;
                 Ok(cells.into_iter().map(Cell::Odt).collect())
             },
         }
@@ -184,6 +548,8 @@ impl Row {
                 let cells = r.cells().map_err(Error::from)?;
                 Ok(cells.get(index).cloned().map(Cell::Docx))
             },
+            #[cfg(feature = "iwa")]
+            Row::Pages(cells) => Ok(cells.get(index).cloned().map(Cell::Pages)),
             #[cfg(feature = "rtf")]
             Row::Rtf(r) => {
                 let cells = r.cells();
@@ -191,9 +557,7 @@ impl Row {
             },
             #[cfg(feature = "odf")]
             Row::Odt(r) => {
-                let cells = r
-                    .cells()
-                    .map_err(|e| Error::ParseError(format!("Failed to get cells: {}", e)))?;
+                let cells = r.cells()?;
                 Ok(cells.get(index).cloned().map(Cell::Odt))
             },
         }
@@ -207,6 +571,8 @@ pub enum Cell {
     Doc(ole::doc::Cell),
     #[cfg(feature = "ooxml")]
     Docx(ooxml::docx::Cell),
+    #[cfg(feature = "iwa")]
+    Pages(crate::iwa::structured::CellValue),
     #[cfg(feature = "rtf")]
     Rtf(crate::rtf::Cell<'static>),
     #[cfg(feature = "odf")]
@@ -221,31 +587,85 @@ impl Cell {
             Cell::Doc(c) => c.text().map(|s| s.to_string()).map_err(Error::from),
             #[cfg(feature = "ooxml")]
             Cell::Docx(c) => c.text().map(|s| s.to_string()).map_err(Error::from),
+            #[cfg(feature = "iwa")]
+            Cell::Pages(value) => Ok(value.to_string()),
             #[cfg(feature = "rtf")]
             Cell::Rtf(c) => Ok(c.text().to_string()),
             #[cfg(feature = "odf")]
-            Cell::Odt(c) => c
-                .text()
-                .map_err(|e| Error::ParseError(format!("Failed to get cell text: {}", e))),
+            Cell::Odt(c) => c.text(),
+        }
+    }
+
+    /// Get the paragraphs directly in this cell.
+    ///
+    /// Paragraphs of nested tables are left out; see [`Cell::tables`].
+    /// Returns an empty list for formats that only expose cell text (Pages,
+    /// RTF and OpenDocument).
+    pub fn paragraphs(&self) -> Result<Vec<Paragraph>> {
+        Ok(self
+            .elements()?
+            .into_iter()
+            .filter_map(|element| match element {
+                DocumentElement::Paragraph(para) => Some(*para),
+                DocumentElement::Table(_) => None,
+            })
+            .collect())
+    }
+
+    /// Get the tables nested directly in this cell.
+    pub fn tables(&self) -> Result<Vec<Table>> {
+        match self {
+            #[cfg(feature = "ole")]
+            Cell::Doc(c) => Ok(c
+                .tables()
+                .map_err(Error::from)?
+                .into_iter()
+                .map(Table::Doc)
+                .collect()),
+            #[cfg(feature = "ooxml")]
+            Cell::Docx(c) => Ok(c
+                .tables()
+                .map_err(Error::from)?
+                .into_iter()
+                .map(|t| Table::Docx(Box::new(t)))
+                .collect()),
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the paragraphs and nested tables of this cell in document order.
+    ///
+    /// Returns an empty list for formats that only expose cell text (Pages,
+    /// RTF and OpenDocument).
+    pub fn elements(&self) -> Result<Vec<DocumentElement>> {
+        match self {
+            #[cfg(feature = "ole")]
+            Cell::Doc(c) => c.elements().map_err(Error::from),
+            #[cfg(feature = "ooxml")]
+            Cell::Docx(c) => c.elements().map_err(Error::from),
+            #[allow(unreachable_patterns)]
+            _ => Ok(Vec::new()),
         }
     }
 
     /// Get the grid span (colspan) of this cell.
     ///
-    /// Returns the number of columns this cell spans. Default is 1 (no merge).
+    /// Returns the number of grid columns this cell spans. Default is 1 (no merge).
     ///
-    /// **Note**: Currently only implemented for OOXML (.docx) format.
-    /// Other formats always return 1.
+    /// **Note**: Pages and RTF tables always return 1.
     pub fn grid_span(&self) -> Result<usize> {
         match self {
             #[cfg(feature = "ole")]
-            Cell::Doc(_) => Ok(1), // Not implemented for OLE format
+            Cell::Doc(c) => Ok(c.grid_span()),
             #[cfg(feature = "ooxml")]
             Cell::Docx(c) => c.grid_span().map_err(Error::from),
+            #[cfg(feature = "iwa")]
+            Cell::Pages(_) => Ok(1), // Merged ranges are not decoded for Pages tables
             #[cfg(feature = "rtf")]
             Cell::Rtf(_) => Ok(1), // Not implemented for RTF format
             #[cfg(feature = "odf")]
-            Cell::Odt(_) => Ok(1), // Grid span not available in ODF format (always 1)
+            Cell::Odt(c) => Ok(c.colspan()),
         }
     }
 
@@ -254,20 +674,166 @@ impl Cell {
     /// Returns the vertical merge state if this cell participates in vertical merging,
     /// or `None` if no vertical merge is present.
     ///
-    /// **Note**: Currently only implemented for OOXML (.docx) format.
-    /// Other formats always return `None`.
+    /// **Note**: Only .doc and .docx cells store vertical merges this way.
+    /// Other formats always return `None`; OpenDocument stores row spans,
+    /// see [`Table::cell_placements`].
     #[cfg(feature = "ooxml")]
     pub fn v_merge(&self) -> Result<Option<crate::ooxml::docx::VMergeState>> {
         match self {
             #[cfg(feature = "ole")]
-            Cell::Doc(_) => Ok(None), // Not implemented for OLE format
+            Cell::Doc(c) => {
+                use crate::ole::doc::parts::tap::CellMergeStatus;
+                use crate::ooxml::docx::VMergeState;
+
+                Ok(c.properties().and_then(|p| match p.vertical_merge {
+                    CellMergeStatus::None => None,
+                    CellMergeStatus::First => Some(VMergeState::Restart),
+                    CellMergeStatus::Merged => Some(VMergeState::Continue),
+                }))
+            },
             Cell::Docx(c) => c.v_merge().map_err(Error::from),
+            #[cfg(feature = "iwa")]
+            Cell::Pages(_) => Ok(None), // Merged ranges are not decoded for Pages tables
             #[cfg(feature = "rtf")]
             Cell::Rtf(_) => Ok(None), // Not implemented for RTF format
             #[cfg(feature = "odf")]
             Cell::Odt(_) => Ok(None), // Vertical merge not available in ODF format
         }
     }
+
+    /// Get the background color of this cell.
+    ///
+    /// Returns `None` when the cell has no shading of its own, or for formats
+    /// that do not record it (Pages, RTF and OpenDocument).
+    pub fn shading(&self) -> Result<Option<RGBColor>> {
+        match self {
+            #[cfg(feature = "ole")]
+            Cell::Doc(c) => Ok(c.background_color().map(|(r, g, b)| RGBColor::new(r, g, b))),
+            #[cfg(feature = "ooxml")]
+            Cell::Docx(c) => Ok(c.properties().map_err(Error::from)?.shading),
+            #[allow(unreachable_patterns)]
+            _ => Ok(None),
+        }
+    }
+
+    /// Get the borders this cell sets for itself.
+    ///
+    /// Formats that do not record cell borders (Pages, RTF and OpenDocument)
+    /// return no borders.
+    pub fn borders(&self) -> Result<CellBorders> {
+        match self {
+            #[cfg(feature = "ole")]
+            Cell::Doc(c) => {
+                let Some(borders) = c.properties().map(|p| &p.borders) else {
+                    return Ok(CellBorders::default());
+                };
+                Ok(CellBorders {
+                    top: borders.top.as_ref().and_then(doc_border),
+                    left: borders.left.as_ref().and_then(doc_border),
+                    bottom: borders.bottom.as_ref().and_then(doc_border),
+                    right: borders.right.as_ref().and_then(doc_border),
+                })
+            },
+            #[cfg(feature = "ooxml")]
+            Cell::Docx(c) => Ok(c.properties().map_err(Error::from)?.borders),
+            #[allow(unreachable_patterns)]
+            _ => Ok(CellBorders::default()),
+        }
+    }
+
+    /// Get the vertical alignment of the content of this cell.
+    ///
+    /// Returns `None` when the cell does not set it, or for formats that do
+    /// not record it (Pages, RTF and OpenDocument).
+    pub fn vertical_alignment(&self) -> Result<Option<CellVerticalAlignment>> {
+        match self {
+            #[cfg(feature = "ole")]
+            Cell::Doc(c) => {
+                use crate::ole::doc::parts::tap::VerticalAlignment;
+
+                Ok(c.vertical_alignment().map(|alignment| match alignment {
+                    VerticalAlignment::Top => CellVerticalAlignment::Top,
+                    VerticalAlignment::Center => CellVerticalAlignment::Center,
+                    VerticalAlignment::Bottom => CellVerticalAlignment::Bottom,
+                }))
+            },
+            #[cfg(feature = "ooxml")]
+            Cell::Docx(c) => Ok(c.properties().map_err(Error::from)?.vertical_alignment),
+            #[allow(unreachable_patterns)]
+            _ => Ok(None),
+        }
+    }
+}
+
+/// Convert a border of a .doc table cell.
+#[cfg(feature = "ole")]
+fn doc_border(border: &ole::doc::parts::tap::BorderStyle) -> Option<CellBorder> {
+    use crate::ole::doc::parts::tap::BorderType;
+
+    let style = match border.border_type {
+        BorderType::None => return None,
+        BorderType::Single => BorderLineStyle::Single,
+        BorderType::Thick => BorderLineStyle::Thick,
+        BorderType::Double => BorderLineStyle::Double,
+        BorderType::Triple => BorderLineStyle::Triple,
+        BorderType::Dotted => BorderLineStyle::Dotted,
+        BorderType::Dashed => BorderLineStyle::Dashed,
+        BorderType::DotDash => BorderLineStyle::DotDash,
+        BorderType::DotDotDash => BorderLineStyle::DotDotDash,
+        _ => BorderLineStyle::Other,
+    };
+    Some(CellBorder {
+        style,
+        // Widths are stored in eighths of a point
+        width: Length::from_points(f64::from(border.width) / 8.0),
+        color: border.color.map(|(r, g, b)| RGBColor::new(r, g, b)),
+    })
+}
+
+/// Serializes the table as its rows.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Table {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        let mut state = serializer.serialize_struct("Table", 1)?;
+        state.serialize_field("rows", &self.rows().map_err(S::Error::custom)?)?;
+        state.end()
+    }
+}
+
+/// Serializes the row as its cells.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Row {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        let mut state = serializer.serialize_struct("Row", 1)?;
+        state.serialize_field("cells", &self.cells().map_err(S::Error::custom)?)?;
+        state.end()
+    }
+}
+
+/// Serializes the cell's text and the number of grid columns it spans.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Cell {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        let mut state = serializer.serialize_struct("Cell", 2)?;
+        state.serialize_field("text", &self.text().map_err(S::Error::custom)?)?;
+        state.serialize_field("grid_span", &self.grid_span().map_err(S::Error::custom)?)?;
+        state.end()
+    }
 }
 
 #[cfg(test)]
@@ -440,4 +1006,241 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_cell_placements_merged_header_docx() {
+        use super::CellPlacement;
+
+        let path = test_data_path().join("ooxml/docx/merged_header_table.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let table = &doc.tables().expect("Failed to get tables")[0];
+
+        let at = |row, column, row_span, column_span| {
+            Some(CellPlacement {
+                row,
+                column,
+                row_span,
+                column_span,
+            })
+        };
+        assert_eq!(
+            table.cell_placements().unwrap(),
+            vec![
+                vec![at(0, 0, 2, 1), at(0, 1, 1, 2), at(0, 3, 1, 2)],
+                vec![
+                    None,
+                    at(1, 1, 1, 1),
+                    at(1, 2, 1, 1),
+                    at(1, 3, 1, 1),
+                    at(1, 4, 1, 1)
+                ],
+                vec![
+                    at(2, 0, 1, 1),
+                    at(2, 1, 1, 1),
+                    at(2, 2, 1, 1),
+                    at(2, 3, 2, 2)
+                ],
+                vec![at(3, 1, 1, 1), at(3, 2, 1, 1), None],
+            ]
+        );
+
+        let widths: Vec<i64> = table
+            .column_widths()
+            .unwrap()
+            .iter()
+            .map(|w| w.twips())
+            .collect();
+        assert_eq!(widths, vec![1440, 2000, 1440, 1440, 1440]);
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_cell_formatting_docx() {
+        use super::{BorderLineStyle, CellVerticalAlignment};
+        use crate::common::RGBColor;
+
+        let path = test_data_path().join("ooxml/docx/merged_header_table.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let table = &doc.tables().expect("Failed to get tables")[0];
+        let header = table.rows().unwrap()[0].cells().unwrap();
+
+        let blue = RGBColor::new(0xD9, 0xE2, 0xF3);
+        assert_eq!(header[0].shading().unwrap(), Some(blue));
+        assert_eq!(
+            header[0].vertical_alignment().unwrap(),
+            Some(CellVerticalAlignment::Center)
+        );
+        // A solid pattern shows its foreground color
+        assert_eq!(
+            header[2].shading().unwrap(),
+            Some(RGBColor::new(0, 0xFF, 0))
+        );
+        assert_eq!(
+            header[2].vertical_alignment().unwrap(),
+            Some(CellVerticalAlignment::Bottom)
+        );
+
+        let borders = header[1].borders().unwrap();
+        let top = borders.top.expect("top border");
+        assert_eq!(top.style, BorderLineStyle::Single);
+        assert_eq!(top.width.points(), 1.5);
+        assert_eq!(top.color, Some(RGBColor::new(0xFF, 0, 0)));
+        assert_eq!(borders.left, None);
+        let bottom = borders.bottom.expect("bottom border");
+        assert_eq!(bottom.style, BorderLineStyle::Double);
+        assert_eq!(bottom.color, None);
+        assert_eq!(borders.right, None);
+
+        // The nested table's cell properties do not leak into the outer cell
+        let north = &table.rows().unwrap()[2].cells().unwrap()[0];
+        assert_eq!(north.grid_span().unwrap(), 1);
+        assert_eq!(north.v_merge().unwrap(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_merged_header_html_docx() {
+        use crate::markdown::{MarkdownOptions, TableStyle, ToMarkdown};
+
+        let path = test_data_path().join("ooxml/docx/merged_header_table.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let markdown = doc
+            .to_markdown_with_options(
+                &MarkdownOptions::new().with_table_style(TableStyle::Markdown),
+            )
+            .unwrap();
+
+        assert!(markdown.contains(
+            "<tr><th rowspan=\"2\">Region</th><th colspan=\"2\">Sales</th><th colspan=\"2\">Costs</th></tr>\
+             <tr><td>Q1</td><td>Q2</td><td>Q1</td><td>Q2</td></tr>"
+        ));
+        // The row starting after one grid column gets an empty leading cell,
+        // and the cell continuing the merge is left out
+        assert!(markdown.contains(
+            "<td colspan=\"2\" rowspan=\"2\">5</td></tr><tr><td></td><td>11</td><td>21</td></tr>"
+        ));
+        assert_eq!(markdown.matches("<td>5</td>").count(), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_table_layout_doc() {
+        let path = test_data_path().join("ole/doc/FloatingPictures.doc");
+        let doc = Document::open(&path).expect("Failed to open DOC");
+        let tables = doc.tables().expect("Failed to get tables");
+        let table = tables.first().expect("Expected a table");
+
+        let placements = table.cell_placements().unwrap();
+        assert_eq!(placements.len(), 4);
+        for (row, cells) in placements.iter().enumerate() {
+            assert_eq!(cells.len(), 3);
+            for (column, placement) in cells.iter().enumerate() {
+                let placement = placement.expect("Unmerged cell should have a placement");
+                assert_eq!((placement.row, placement.column), (row, column));
+                assert_eq!((placement.row_span, placement.column_span), (1, 1));
+            }
+        }
+
+        let widths = table.column_widths().unwrap();
+        assert_eq!(widths.len(), 3);
+        assert!(widths.iter().all(|w| w.twips() > 0));
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_nested_tables_docx() {
+        use super::super::DocumentElement;
+
+        let path = test_data_path().join("ooxml/docx/nested_tables.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let tables = doc.tables().expect("Failed to get tables");
+        assert_eq!(tables.len(), 1);
+        let table = &tables[0];
+        // Rows of nested tables are not rows of the outer table
+        assert_eq!(table.row_count().unwrap(), 3);
+
+        let cell = table.rows().unwrap()[1].cell_at(1).unwrap().unwrap();
+        let texts: Vec<String> = cell
+            .paragraphs()
+            .unwrap()
+            .iter()
+            .map(|p| p.text().unwrap())
+            .collect();
+        assert_eq!(texts, vec!["Specs:", "See the figures above"]);
+        let kinds: Vec<bool> = cell
+            .elements()
+            .unwrap()
+            .iter()
+            .map(DocumentElement::is_table)
+            .collect();
+        assert_eq!(kinds, vec![false, true, false]);
+
+        let nested = &cell.tables().unwrap()[0];
+        assert_eq!(nested.row_count().unwrap(), 2);
+        let deepest = nested.rows().unwrap()[1].cell_at(1).unwrap().unwrap();
+        assert_eq!(
+            deepest.tables().unwrap()[0].rows().unwrap()[0]
+                .cell_count()
+                .unwrap(),
+            2
+        );
+    }
+
+    /// Count the characters other than spaces, as Word does, walking into
+    /// nested tables.
+    #[cfg(feature = "ooxml")]
+    fn count_characters(elements: &[super::super::DocumentElement]) -> usize {
+        use super::super::DocumentElement;
+
+        elements
+            .iter()
+            .map(|element| match element {
+                DocumentElement::Paragraph(para) => para
+                    .text()
+                    .unwrap()
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .count(),
+                DocumentElement::Table(table) => table
+                    .rows()
+                    .unwrap()
+                    .iter()
+                    .flat_map(|row| row.cells().unwrap())
+                    .map(|cell| count_characters(&cell.elements().unwrap()))
+                    .sum(),
+            })
+            .sum()
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_nested_tables_character_count_docx() {
+        let path = test_data_path().join("ooxml/docx/nested_tables.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let expected = doc.metadata().unwrap().character_count.unwrap() as usize;
+        assert_eq!(count_characters(&doc.elements().unwrap()), expected);
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_nested_tables_html_docx() {
+        use crate::markdown::{MarkdownOptions, TableStyle, ToMarkdown};
+
+        let path = test_data_path().join("ooxml/docx/nested_tables.docx");
+        let doc = Document::open(&path).expect("Failed to open DOCX");
+        let markdown = doc
+            .to_markdown_with_options(
+                &MarkdownOptions::new().with_table_style(TableStyle::Markdown),
+            )
+            .unwrap();
+
+        assert!(markdown.contains(
+            "<td>Specs:<table><tr><th>Key</th><th>Value</th></tr>\
+             <tr><td>Size</td><td>Large<table><tr><th>L1</th><th>L2</th></tr></table></td></tr>\
+             </table>See the figures above</td>"
+        ));
+        // The nested text appears once, inside its own table
+        assert_eq!(markdown.matches("L1").count(), 1);
+    }
 }
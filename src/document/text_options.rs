@@ -0,0 +1,239 @@
+//! Options for extracting the plain text of a document.
+
+/// Options for [`Document::text_with_options`](super::Document::text_with_options).
+///
+/// Every backend reports the same characters for in-paragraph layout:
+/// tabs are `'\t'`, explicit line breaks are `'\n'`, repeated-space
+/// elements expand to their spaces, soft hyphens are dropped and
+/// non-breaking hyphens are U+2011. These options choose what separates
+/// paragraphs and whether that layout is kept or collapsed.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Document;
+/// use litchi::document::TextOptions;
+///
+/// let doc = Document::open("document.docx")?;
+/// let options = TextOptions::new()
+///     .with_floating_text(true)
+///     .with_paragraph_separator("\n\n");
+/// println!("{}", doc.text_with_options(&options)?);
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextOptions {
+    /// Whether text boxes and shape text are placed after the paragraph
+    /// they are anchored to
+    pub floating_text: bool,
+    /// Text placed between paragraphs (`"\n"` by default)
+    pub paragraph_separator: String,
+    /// Whether tabs, line breaks and runs of spaces are kept as they are
+    /// (the default); when off, each run of white space becomes one space
+    pub preserve_whitespace: bool,
+    /// Whether runs whose direction differs from their paragraph's are
+    /// wrapped in Unicode directional isolates (U+2068 FIRST STRONG
+    /// ISOLATE, U+2069 POP DIRECTIONAL ISOLATE)
+    pub bidi_isolates: bool,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            floating_text: false,
+            paragraph_separator: "\n".to_string(),
+            preserve_whitespace: true,
+            bidi_isolates: false,
+        }
+    }
+}
+
+impl TextOptions {
+    /// Create options that extract the body text only, one paragraph per
+    /// line with white space preserved.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether text boxes and shape text are included after their
+    /// anchor paragraphs.
+    pub fn with_floating_text(mut self, floating_text: bool) -> Self {
+        self.floating_text = floating_text;
+        self
+    }
+
+    /// Set the text placed between paragraphs, such as `"\n\n"` to tell
+    /// paragraph breaks apart from line breaks.
+    pub fn with_paragraph_separator(mut self, separator: impl Into<String>) -> Self {
+        self.paragraph_separator = separator.into();
+        self
+    }
+
+    /// Set whether tabs, line breaks and runs of spaces are kept.
+    ///
+    /// When off, each run of spaces, tabs and line breaks within a
+    /// paragraph becomes a single space and paragraphs are trimmed.
+    /// Non-breaking spaces are kept.
+    pub fn with_preserve_whitespace(mut self, preserve_whitespace: bool) -> Self {
+        self.preserve_whitespace = preserve_whitespace;
+        self
+    }
+
+    /// Set whether embedded opposite-direction runs are isolated.
+    ///
+    /// Text is always extracted in logical order. With isolates on, a
+    /// left-to-right run in a right-to-left paragraph, such as an English
+    /// name in Arabic text, or a right-to-left run in a left-to-right
+    /// paragraph is wrapped in FSI/PDI so that bidi-aware viewers do not
+    /// reorder it with the neutral characters around it. Adjacent runs of
+    /// the same direction share one isolate.
+    pub fn with_bidi_isolates(mut self, bidi_isolates: bool) -> Self {
+        self.bidi_isolates = bidi_isolates;
+        self
+    }
+
+    /// Join the texts of a paragraph's runs, each paired with whether it is
+    /// right-to-left, isolating those against the paragraph direction if
+    /// these options ask.
+    pub(super) fn join_runs(&self, rtl_paragraph: bool, runs: &[(String, bool)]) -> String {
+        if !self.bidi_isolates {
+            return runs.iter().map(|(text, _)| text.as_str()).collect();
+        }
+
+        // White space between two isolated runs belongs to their isolate;
+        // elsewhere it takes the paragraph direction
+        let blank = |text: &str| text.trim().is_empty();
+        let opposite = |index: usize| runs[index].1 != rtl_paragraph;
+        let isolated: Vec<bool> = (0..runs.len())
+            .map(|index| {
+                if !blank(&runs[index].0) {
+                    return opposite(index);
+                }
+                let before = runs[..index].iter().rposition(|(text, _)| !blank(text));
+                let after = runs[index + 1..]
+                    .iter()
+                    .position(|(text, _)| !blank(text))
+                    .map(|offset| index + 1 + offset);
+                matches!((before, after), (Some(before), Some(after))
+                    if opposite(before) && opposite(after))
+            })
+            .collect();
+
+        let mut result = String::new();
+        let mut open = false;
+        for ((text, _), isolated) in runs.iter().zip(isolated) {
+            if isolated != open {
+                result.push(if isolated { '\u{2068}' } else { '\u{2069}' });
+                open = isolated;
+            }
+            result.push_str(text);
+        }
+        if open {
+            // Close the isolate before the paragraph mark of .doc runs
+            let end = result.trim_end_matches(['\r', '\n', '\u{7}']).len();
+            result.insert(end, '\u{2069}');
+        }
+        result
+    }
+
+    /// Normalize the text of one paragraph as these options ask.
+    ///
+    /// Word binary control characters are mapped first: vertical tabs
+    /// (line breaks) become `'\n'`, `0x1E` becomes U+2011 and soft hyphens
+    /// (`0x1F`) are dropped.
+    pub(super) fn paragraph_text(&self, text: &str) -> String {
+        let text = text.trim_end_matches(['\r', '\n', '\u{7}']);
+        let mapped = text.chars().filter_map(|ch| match ch {
+            '\u{b}' => Some('\n'),
+            '\u{1e}' => Some('\u{2011}'),
+            '\u{1f}' => None,
+            ch => Some(ch),
+        });
+        if self.preserve_whitespace {
+            return mapped.collect();
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut pending_space = false;
+        for ch in mapped {
+            if matches!(ch, ' ' | '\t' | '\n' | '\r') {
+                pending_space = !result.is_empty();
+            } else {
+                if pending_space {
+                    result.push(' ');
+                    pending_space = false;
+                }
+                result.push(ch);
+            }
+        }
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paragraph_text_preserves_whitespace() {
+        let options = TextOptions::new();
+        assert_eq!(
+            options.paragraph_text("Name:\tValue\u{b}next  line\r"),
+            "Name:\tValue\nnext  line"
+        );
+        assert_eq!(
+            options.paragraph_text("well\u{1e}known hy\u{1f}phen\u{7}"),
+            "well\u{2011}known hyphen"
+        );
+    }
+
+    #[test]
+    fn test_join_runs_isolates_opposite_runs() {
+        let runs = |runs: &[(&str, bool)]| -> Vec<(String, bool)> {
+            runs.iter()
+                .map(|(text, rtl)| (text.to_string(), *rtl))
+                .collect()
+        };
+        let options = TextOptions::new().with_bidi_isolates(true);
+
+        // An English name in an Arabic paragraph, split over two runs
+        let arabic = runs(&[
+            ("\u{645}\u{631}\u{62d}\u{628}\u{627} ", true),
+            ("Litchi", false),
+            (" ", false),
+            ("2.0", false),
+            (" \u{634}\u{643}\u{631}\u{627}\r", true),
+        ]);
+        assert_eq!(
+            options.join_runs(true, &arabic),
+            "\u{645}\u{631}\u{62d}\u{628}\u{627} \u{2068}Litchi 2.0\u{2069} \u{634}\u{643}\u{631}\u{627}\r"
+        );
+        // Off by default, and nothing to isolate in a paragraph of one direction
+        assert_eq!(
+            TextOptions::new().join_runs(true, &arabic),
+            "\u{645}\u{631}\u{62d}\u{628}\u{627} Litchi 2.0 \u{634}\u{643}\u{631}\u{627}\r"
+        );
+        assert_eq!(
+            options.join_runs(false, &runs(&[("plain ", false), ("text", false)])),
+            "plain text"
+        );
+
+        // A trailing Hebrew run closes its isolate before the paragraph mark
+        assert_eq!(
+            options.join_runs(
+                false,
+                &runs(&[("see ", false), ("\u{5e9}\u{5dc}\u{5d5}\u{5dd}\r", true)])
+            ),
+            "see \u{2068}\u{5e9}\u{5dc}\u{5d5}\u{5dd}\u{2069}\r"
+        );
+    }
+
+    #[test]
+    fn test_paragraph_text_collapses_whitespace() {
+        let options = TextOptions::new().with_preserve_whitespace(false);
+        assert_eq!(
+            options.paragraph_text("  Name:\tValue\nnext   line  "),
+            "Name: Value next line"
+        );
+    }
+}
@@ -6,6 +6,10 @@ use std::io::{Read, Seek};
 
 #[cfg(feature = "ole")]
 use crate::ole;
+#[cfg(feature = "ole")]
+use once_cell::sync::OnceCell;
+#[cfg(feature = "ole")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 #[cfg(feature = "ooxml")]
 use crate::ooxml;
@@ -19,7 +23,7 @@ use crate::ooxml;
 pub(super) enum DocumentImpl {
     /// Legacy .doc format
     #[cfg(feature = "ole")]
-    Doc(ole::doc::Document, crate::common::Metadata),
+    Doc(DocFile, crate::common::Metadata),
     /// Modern .docx format
     #[cfg(feature = "ooxml")]
     Docx(Box<ooxml::docx::Document<'static>>, crate::common::Metadata),
@@ -34,6 +38,38 @@ pub(super) enum DocumentImpl {
     Odt(crate::odf::Document),
 }
 
+/// An open .doc file whose document is parsed on first use.
+///
+/// The package is kept open so that text can be streamed from it without
+/// parsing the whole document.
+#[cfg(feature = "ole")]
+pub(super) struct DocFile<R: Read + Seek = ole::OleSource> {
+    package: Mutex<ole::doc::Package<R>>,
+    document: OnceCell<ole::doc::Document>,
+}
+
+#[cfg(feature = "ole")]
+impl<R: Read + Seek> DocFile<R> {
+    pub(super) fn new(package: ole::doc::Package<R>) -> Self {
+        Self {
+            package: Mutex::new(package),
+            document: OnceCell::new(),
+        }
+    }
+
+    /// Get the parsed document, parsing it on the first call.
+    pub(super) fn document(&self) -> Result<&ole::doc::Document> {
+        self.document
+            .get_or_try_init(|| self.package().document().map_err(Error::from))
+    }
+
+    /// Lock the package for reading its streams.
+    pub(super) fn package(&self) -> MutexGuard<'_, ole::doc::Package<R>> {
+        // A panic while reading leaves the reader usable, since every read seeks first
+        self.package.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
 /// Document format detection.
 ///
 /// This enum represents the supported document formats in the unified
@@ -1,34 +1,127 @@
+use std::any::Any;
+use std::path::Path;
+use std::sync::Arc;
+
+use font_kit::error::SelectionError;
+use font_kit::family_handle::FamilyHandle;
 use font_kit::family_name::FamilyName;
+use font_kit::file_type::FileType;
+use font_kit::font::Font;
 use font_kit::handle::Handle;
-use font_kit::properties::Properties;
-use font_kit::source::SystemSource;
+use font_kit::properties::{Properties, Style, Weight};
+use font_kit::source::{Source, SystemSource};
 
 use crate::fonts::{FontData, FontError, FontProperties};
 
+/// File extensions of the fonts read from font directories
+const FONT_EXTENSIONS: [&str; 4] = ["ttf", "otf", "ttc", "otc"];
+
+/// Finds fonts by family name.
+///
+/// Fonts are looked up in this order, and the first of these that has the
+/// family is used:
+///
+/// 1. fonts registered from memory with [`register_font`](Self::register_font)
+/// 2. fonts in directories added with [`add_font_dir`](Self::add_font_dir)
+/// 3. the fonts installed on the system
+///
+/// The face closest to the requested weight and style is picked from the
+/// family. Family names of registered and directory fonts are compared
+/// ignoring case, spaces and hyphens, and the full name of a face, such as
+/// "Corporate Sans Bold", selects that face.
 pub struct FontLoader {
+    registered: FontSet,
+    directories: FontSet,
     source: SystemSource,
 }
 
 impl FontLoader {
     pub fn new() -> Self {
         Self {
+            registered: FontSet::default(),
+            directories: FontSet::default(),
             source: SystemSource::new(),
         }
     }
 
+    /// Register font data, such as a font shipped with the application,
+    /// under its own family name and `name`.
+    ///
+    /// Every face of a font collection is registered. Returns an error if
+    /// the data is not a font.
+    pub fn register_font(
+        &mut self,
+        name: &str,
+        data: impl Into<Arc<Vec<u8>>>,
+    ) -> Result<(), FontError> {
+        let data = data.into();
+        let faces = match Font::analyze_bytes(data.clone()) {
+            Ok(FileType::Single) => 1,
+            Ok(FileType::Collection(count)) => count,
+            Err(_) => return Err(FontError::InvalidData),
+        };
+        for index in 0..faces {
+            let handle = Handle::from_memory(data.clone(), index);
+            self.registered.add(handle, Some(name))?;
+        }
+        Ok(())
+    }
+
+    /// Search a directory and its subdirectories for TrueType and
+    /// OpenType fonts, and return the number of faces found.
+    ///
+    /// Files that cannot be read as fonts are skipped.
+    pub fn add_font_dir(&mut self, dir: impl AsRef<Path>) -> Result<usize, FontError> {
+        let mut paths = Vec::new();
+        font_files(dir.as_ref(), &mut paths)?;
+        let before = self.directories.faces.len();
+        for path in paths {
+            let faces = match Font::analyze_path(&path) {
+                Ok(FileType::Single) => 1,
+                Ok(FileType::Collection(count)) => count,
+                Err(_) => continue,
+            };
+            for index in 0..faces {
+                let _ = self
+                    .directories
+                    .add(Handle::from_path(path.clone(), index), None);
+            }
+        }
+        Ok(self.directories.faces.len() - before)
+    }
+
     pub fn load_system_font(&self, family_name: &str) -> Result<FontData, FontError> {
-        let handle = self
-            .source
-            .select_best_match(
-                &[FamilyName::Title(family_name.to_string())],
-                &Properties::new(),
-            )
-            .map_err(|_| FontError::NotFound(family_name.to_string()))?;
+        self.load_system_font_with_style(family_name, false, false)
+    }
+
+    /// Load the closest bold and/or italic face of a font family.
+    ///
+    /// Falls back to the nearest face the family has, so a family without
+    /// an italic face loads its regular face for `italic`.
+    pub fn load_system_font_with_style(
+        &self,
+        family_name: &str,
+        bold: bool,
+        italic: bool,
+    ) -> Result<FontData, FontError> {
+        let mut properties = Properties::new();
+        if bold {
+            properties.weight = Weight::BOLD;
+        }
+        if italic {
+            properties.style = Style::Italic;
+        }
+        let family = [FamilyName::Title(family_name.to_string())];
+        let sources: [&dyn Source; 3] = [&self.registered, &self.directories, &self.source];
+        let handle = sources
+            .iter()
+            .find_map(|source| source.select_best_match(&family, &properties).ok())
+            .ok_or_else(|| FontError::NotFound(family_name.to_string()))?;
 
         match handle {
             Handle::Path { path, font_index } => {
                 let data = std::fs::read(&path)?;
-                let properties = Self::extract_font_properties(&data);
+                let properties = Self::extract_font_properties(&data, font_index);
                 Ok(FontData {
                     name: family_name.to_string(),
                     data,
@@ -37,10 +130,10 @@ impl FontLoader {
                 })
             },
             Handle::Memory { bytes, font_index } => {
-                let properties = Self::extract_font_properties(&bytes);
+                let properties = Self::extract_font_properties(&bytes, font_index);
                 Ok(FontData {
                     name: family_name.to_string(),
-                    data: bytes.to_vec(),
+                    data: Arc::unwrap_or_clone(bytes),
                     index: font_index,
                     properties,
                 })
@@ -48,14 +141,15 @@ impl FontLoader {
         }
     }
 
-    /// Extract font properties from font data for Office embedding
-    fn extract_font_properties(data: &[u8]) -> Option<FontProperties> {
+    /// Extract font properties of the face at `index` for Office embedding
+    fn extract_font_properties(data: &[u8], index: u32) -> Option<FontProperties> {
         use allsorts::binary::read::ReadScope;
         use allsorts::tables::{FontTableProvider, OpenTypeFont};
 
         let scope = ReadScope::new(data);
         let font_file = scope.read::<OpenTypeFont<'_>>().ok()?;
-        let provider = font_file.table_provider(0).ok()?;
+        // The high 16 bits select a named instance, not a face
+        let provider = font_file.table_provider((index & 0xFFFF) as usize).ok()?;
 
         // Get OS/2 table raw bytes
         let os2_table = provider.table_data(allsorts::tag::OS_2).ok()??;
@@ -170,3 +264,195 @@ impl Default for FontLoader {
         Self::new()
     }
 }
+
+/// A face added to a [`FontSet`].
+struct Face {
+    handle: Handle,
+    /// Family and full names, and the registered name, as compared by
+    /// [`name_key`]
+    names: Vec<String>,
+    postscript_name: Option<String>,
+}
+
+/// Registered fonts or fonts from directories, searched with font-kit's
+/// matching of weight and style.
+#[derive(Default)]
+struct FontSet {
+    faces: Vec<Face>,
+}
+
+impl FontSet {
+    fn add(&mut self, handle: Handle, name: Option<&str>) -> Result<(), FontError> {
+        let font = Font::from_handle(&handle).map_err(|_| FontError::InvalidData)?;
+        let mut names: Vec<String> = [Some(font.family_name()), Some(font.full_name())]
+            .into_iter()
+            .chain([name.map(str::to_string)])
+            .flatten()
+            .map(|name| name_key(&name))
+            .filter(|name| !name.is_empty())
+            .collect();
+        names.dedup();
+        self.faces.push(Face {
+            handle,
+            names,
+            postscript_name: font.postscript_name(),
+        });
+        Ok(())
+    }
+}
+
+impl Source for FontSet {
+    fn all_fonts(&self) -> Result<Vec<Handle>, SelectionError> {
+        Ok(self.faces.iter().map(|face| face.handle.clone()).collect())
+    }
+
+    fn all_families(&self) -> Result<Vec<String>, SelectionError> {
+        let mut families: Vec<String> = self
+            .faces
+            .iter()
+            .filter_map(|face| face.names.first().cloned())
+            .collect();
+        families.sort();
+        families.dedup();
+        Ok(families)
+    }
+
+    fn select_family_by_name(&self, family_name: &str) -> Result<FamilyHandle, SelectionError> {
+        let key = name_key(family_name);
+        let handles: Vec<Handle> = self
+            .faces
+            .iter()
+            .filter(|face| face.names.contains(&key))
+            .map(|face| face.handle.clone())
+            .collect();
+        if handles.is_empty() {
+            return Err(SelectionError::NotFound);
+        }
+        Ok(FamilyHandle::from_font_handles(handles.into_iter()))
+    }
+
+    fn select_by_postscript_name(&self, postscript_name: &str) -> Result<Handle, SelectionError> {
+        self.faces
+            .iter()
+            .find(|face| face.postscript_name.as_deref() == Some(postscript_name))
+            .map(|face| face.handle.clone())
+            .ok_or(SelectionError::NotFound)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// A font name in lower case without spaces, hyphens and underscores, so
+/// that "Corporate Sans" matches "corporate-sans".
+fn name_key(name: &str) -> String {
+    name.chars()
+        .filter(|ch| !matches!(ch, ' ' | '-' | '_'))
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Collect the font files in a directory and its subdirectories.
+fn font_files(dir: &Path, paths: &mut Vec<std::path::PathBuf>) -> Result<(), FontError> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            // Unreadable subdirectories are skipped
+            let _ = font_files(&path, paths);
+        } else if path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| FONT_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()))
+        {
+            paths.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Regular and bold faces of an installed font with separate files.
+    fn system_faces() -> Option<(Vec<u8>, Vec<u8>)> {
+        let loader = FontLoader::new();
+        let regular = loader.load_system_font("sans-serif").ok()?;
+        let bold = loader
+            .load_system_font_with_style("sans-serif", true, false)
+            .ok()?;
+        (regular.data != bold.data && regular.index == 0 && bold.index == 0)
+            .then_some((regular.data, bold.data))
+    }
+
+    #[test]
+    fn test_name_key_ignores_case_and_separators() {
+        assert_eq!(name_key("Corporate Sans"), "corporatesans");
+        assert_eq!(name_key("corporate-sans"), "corporatesans");
+        assert_eq!(name_key("CORPORATE_SANS"), "corporatesans");
+    }
+
+    #[test]
+    fn test_registered_fonts_match_weight() {
+        let Some((regular, bold)) = system_faces() else {
+            // No font with a bold face installed
+            return;
+        };
+        let mut loader = FontLoader::new();
+        loader
+            .register_font("Corporate Sans", regular.clone())
+            .unwrap();
+        loader
+            .register_font("Corporate Sans", bold.clone())
+            .unwrap();
+        assert!(matches!(
+            loader.register_font("Broken", vec![0u8; 16]),
+            Err(FontError::InvalidData)
+        ));
+
+        let font = loader.load_system_font("corporate-sans").unwrap();
+        assert_eq!(font.data, regular);
+        assert_eq!(font.name, "corporate-sans");
+        let font = loader
+            .load_system_font_with_style("Corporate Sans", true, false)
+            .unwrap();
+        assert_eq!(font.data, bold);
+        // Without an italic face, the closest face is used
+        let font = loader
+            .load_system_font_with_style("Corporate Sans", true, true)
+            .unwrap();
+        assert_eq!(font.data, bold);
+    }
+
+    #[test]
+    fn test_font_dirs_are_searched_before_system_fonts() {
+        let Some((regular, bold)) = system_faces() else {
+            return;
+        };
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("nested")).unwrap();
+        std::fs::write(dir.path().join("nested").join("Face.TTF"), &bold).unwrap();
+        std::fs::write(dir.path().join("notes.txt"), b"not a font").unwrap();
+        std::fs::write(dir.path().join("broken.ttf"), b"not a font").unwrap();
+
+        let mut loader = FontLoader::new();
+        assert_eq!(loader.add_font_dir(dir.path()).unwrap(), 1);
+        assert!(loader.add_font_dir(dir.path().join("missing")).is_err());
+
+        // The directory's bold face is the only face of its family there
+        let family = Font::from_bytes(Arc::new(bold.clone()), 0)
+            .unwrap()
+            .family_name();
+        let font = loader.load_system_font(&family).unwrap();
+        assert_eq!(font.data, bold);
+
+        // Registered fonts come first
+        loader.register_font(&family, regular.clone()).unwrap();
+        assert_eq!(loader.load_system_font(&family).unwrap().data, regular);
+    }
+}
@@ -35,6 +35,25 @@ pub struct FontData {
     pub properties: Option<FontProperties>,
 }
 
+#[cfg(feature = "fonts")]
+impl FontData {
+    /// Index of the face within a font collection (.ttc/.otc).
+    ///
+    /// fontconfig stores the named instance of a variable font in the high
+    /// 16 bits of `index`, so only the low 16 bits select the face.
+    pub fn face_index(&self) -> usize {
+        (self.index & 0xFFFF) as usize
+    }
+
+    /// Named instance of a variable font selected by `index`, counting
+    /// from 0, or `None` for the default instance.
+    pub fn named_instance(&self) -> Option<usize> {
+        (self.index >> 16)
+            .checked_sub(1)
+            .map(|instance| instance as usize)
+    }
+}
+
 /// Font properties needed for Office font embedding
 #[cfg(feature = "fonts")]
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -1,7 +1,12 @@
 use allsorts::{
     binary::read::ReadScope,
     font_data::FontData as AllsortsFontData,
-    subset::{CmapTarget, SubsetProfile, subset},
+    subset::{CmapTarget, SubsetProfile, subset, whole_font},
+    tables::{
+        Fixed, FontTableProvider, MaxpTable, OpenTypeData, OpenTypeFont,
+        variable_fonts::fvar::FvarTable,
+    },
+    tag, variations,
 };
 
 use crate::fonts::{FontData, FontError, FontSubsetter};
@@ -18,28 +23,339 @@ impl AllsortsSubsetter {
     pub fn new() -> Self {
         Self
     }
+
+    /// A standalone font of the whole face, for embedding without
+    /// subsetting.
+    ///
+    /// A variable font is instanced like [`subset`](FontSubsetter::subset)
+    /// does, keeping its variation tables if that fails, and a member of a
+    /// collection is copied out of it. Glyph ids stay the same. Other fonts
+    /// are returned as they are.
+    pub fn whole_face(&self, font: &FontData) -> Result<Vec<u8>, FontError> {
+        if let Ok(Some(instance)) = static_instance(font) {
+            return Ok(instance);
+        }
+
+        let scope = ReadScope::new(&font.data);
+        let font_file = scope.read::<OpenTypeFont<'_>>().map_err(failed)?;
+        if matches!(font_file.data, OpenTypeData::Single(_)) {
+            return Ok(font.data.clone());
+        }
+        let provider = font_file
+            .table_provider(font.face_index())
+            .map_err(failed)?;
+        let tags = provider.table_tags().ok_or(FontError::InvalidData)?;
+        whole_font(&provider, &tags).map_err(failed)
+    }
 }
 
 impl FontSubsetter for AllsortsSubsetter {
+    /// Subset the face selected by `font.index` to `glyph_ids`, which must
+    /// start with glyph 0 (`.notdef`).
+    ///
+    /// Variable fonts are first instanced at their named or default
+    /// instance, as the variation tables cannot follow the renumbered
+    /// glyphs. CFF outlines are subset as CFF. The result is parsed again,
+    /// so a font that allsorts cannot subset correctly fails here instead
+    /// of being embedded corrupt.
     fn subset(&self, font: &FontData, glyph_ids: &[u16]) -> Result<Vec<u8>, FontError> {
-        let scope = ReadScope::new(&font.data);
-        let font_data = scope
-            .read::<AllsortsFontData>()
-            .map_err(|e| FontError::SubsettingFailed(e.to_string()))?;
+        let instance = static_instance(font)?;
+        let (data, index) = match &instance {
+            Some(instance) => (instance.as_slice(), 0),
+            None => (font.data.as_slice(), font.face_index()),
+        };
 
-        let provider = font_data
-            .table_provider(font.index as usize)
-            .map_err(|e| FontError::SubsettingFailed(e.to_string()))?;
+        let scope = ReadScope::new(data);
+        let font_data = scope.read::<AllsortsFontData>().map_err(failed)?;
+        let provider = font_data.table_provider(index).map_err(failed)?;
 
-        // Basic subsetting with PDF profile and default CmapTarget
+        // The smallest cmap can be a Mac Roman one, which Windows ignores,
+        // so keep a Unicode cmap
         let subset_font = subset(
             &provider,
             glyph_ids,
             &SubsetProfile::Pdf,
-            CmapTarget::default(),
+            CmapTarget::Unicode,
         )
-        .map_err(|e| FontError::SubsettingFailed(e.to_string()))?;
+        .map_err(failed)?;
 
+        check_subset(&subset_font, glyph_ids.len())?;
         Ok(subset_font)
     }
 }
+
+/// A static font of the named or default instance of a variable face, or
+/// `None` if the face has no variations.
+fn static_instance(font: &FontData) -> Result<Option<Vec<u8>>, FontError> {
+    let scope = ReadScope::new(&font.data);
+    let font_data = scope.read::<AllsortsFontData>().map_err(failed)?;
+    let provider = font_data
+        .table_provider(font.face_index())
+        .map_err(failed)?;
+    let Some(fvar_data) = provider.table_data(tag::FVAR).map_err(failed)? else {
+        return Ok(None);
+    };
+    let fvar = ReadScope::new(&fvar_data)
+        .read::<FvarTable<'_>>()
+        .map_err(failed)?;
+
+    let coordinates: Vec<Fixed> = match font.named_instance() {
+        Some(index) => {
+            let instance = fvar
+                .instances()
+                .nth(index)
+                .ok_or(FontError::InvalidData)?
+                .map_err(failed)?;
+            instance.coordinates.iter().collect()
+        },
+        None => fvar.axes().map(|axis| axis.default_value).collect(),
+    };
+    let (instance, _) = variations::instance(&provider, &coordinates).map_err(failed)?;
+    Ok(Some(instance))
+}
+
+/// Check that a subset font parses and has a character map and at least
+/// the requested glyphs; components of composite glyphs are added to them.
+fn check_subset(data: &[u8], glyph_count: usize) -> Result<(), FontError> {
+    let scope = ReadScope::new(data);
+    let font_file = scope.read::<OpenTypeFont<'_>>().map_err(failed)?;
+    let provider = font_file.table_provider(0).map_err(failed)?;
+    let maxp = ReadScope::new(&provider.read_table_data(tag::MAXP).map_err(failed)?)
+        .read::<MaxpTable>()
+        .map_err(failed)?;
+    if usize::from(maxp.num_glyphs) < glyph_count || !provider.has_table(tag::CMAP) {
+        return Err(FontError::SubsettingFailed(
+            "subset font does not have the requested glyphs".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+fn failed(error: impl std::fmt::Display) -> FontError {
+    FontError::SubsettingFailed(error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fonts::FontLoader;
+    use allsorts::font::find_good_cmap_subtable;
+    use allsorts::tables::HeadTable;
+    use allsorts::tables::cmap::{Cmap, CmapSubtable};
+
+    type Tables = Vec<([u8; 4], Vec<u8>)>;
+
+    /// Tables of a single-font file, in directory order.
+    fn read_tables(data: &[u8]) -> Tables {
+        let be16 = |pos: usize| u16::from_be_bytes([data[pos], data[pos + 1]]) as usize;
+        let be32 = |pos: usize| u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        (0..be16(4))
+            .map(|i| {
+                let record = 12 + i * 16;
+                let (offset, length) = (be32(record + 8), be32(record + 12));
+                (
+                    data[record..record + 4].try_into().unwrap(),
+                    data[offset..offset + length].to_vec(),
+                )
+            })
+            .collect()
+    }
+
+    /// Write fonts as one font file, or as a collection if there are
+    /// several. Checksums are left at zero.
+    fn write_fonts(fonts: &[Tables]) -> Vec<u8> {
+        let collection = fonts.len() > 1;
+        let header = if collection { 12 + 4 * fonts.len() } else { 0 };
+        let directories: Vec<usize> = fonts.iter().map(|tables| 12 + 16 * tables.len()).collect();
+        let mut out = Vec::new();
+        if collection {
+            out.extend_from_slice(b"ttcf");
+            out.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+            out.extend_from_slice(&(fonts.len() as u32).to_be_bytes());
+            let mut offset = header;
+            for size in &directories {
+                out.extend_from_slice(&(offset as u32).to_be_bytes());
+                offset += size;
+            }
+        }
+
+        let mut data_offset = header + directories.iter().sum::<usize>();
+        let mut data = Vec::new();
+        for tables in fonts {
+            out.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+            out.extend_from_slice(&(tables.len() as u16).to_be_bytes());
+            out.extend_from_slice(&[0; 6]);
+            let mut sorted: Vec<_> = tables.iter().collect();
+            sorted.sort_by_key(|(tag, _)| *tag);
+            for (tag, table) in sorted {
+                out.extend_from_slice(tag);
+                out.extend_from_slice(&0u32.to_be_bytes());
+                out.extend_from_slice(&(data_offset as u32).to_be_bytes());
+                out.extend_from_slice(&(table.len() as u32).to_be_bytes());
+                data.extend_from_slice(table);
+                let padded = table.len().next_multiple_of(4);
+                data.resize(data.len() + padded - table.len(), 0);
+                data_offset += padded;
+            }
+        }
+        out.extend_from_slice(&data);
+        out
+    }
+
+    fn font_data(data: Vec<u8>, index: u32) -> FontData {
+        FontData {
+            name: "Test".to_string(),
+            data,
+            index,
+            properties: None,
+        }
+    }
+
+    fn system_font(bold: bool) -> Option<FontData> {
+        let font = FontLoader::new()
+            .load_system_font_with_style("sans-serif", bold, false)
+            .ok()?;
+        // Collections and variable fonts are built by the tests themselves
+        let single = &font.data[..4] != b"ttcf" && font.index == 0;
+        let tables = single.then(|| read_tables(&font.data))?;
+        let glyf = tables.iter().any(|(tag, _)| tag == b"glyf");
+        let fvar = tables.iter().any(|(tag, _)| tag == b"fvar");
+        (glyf && !fvar).then_some(font)
+    }
+
+    /// Glyph of a character in the face at `index`, through the font's
+    /// Unicode character map.
+    fn glyph_for(data: &[u8], index: usize, ch: char) -> u16 {
+        let font_file = ReadScope::new(data).read::<OpenTypeFont<'_>>().unwrap();
+        let provider = font_file.table_provider(index).unwrap();
+        let cmap_data = provider.read_table_data(tag::CMAP).unwrap();
+        let cmap = ReadScope::new(&cmap_data).read::<Cmap<'_>>().unwrap();
+        let (_, record) = find_good_cmap_subtable(&cmap).unwrap();
+        let subtable = ReadScope::new(&cmap_data)
+            .offset(record.offset as usize)
+            .read::<CmapSubtable<'_>>()
+            .unwrap();
+        subtable.map_glyph(ch as u32).unwrap().unwrap_or(0)
+    }
+
+    fn head(data: &[u8], index: usize) -> HeadTable {
+        let font_file = ReadScope::new(data).read::<OpenTypeFont<'_>>().unwrap();
+        let provider = font_file.table_provider(index).unwrap();
+        let head = provider.read_table_data(tag::HEAD).unwrap();
+        ReadScope::new(&head).read::<HeadTable>().unwrap()
+    }
+
+    fn has_table(data: &[u8], table: u32) -> bool {
+        let font_file = ReadScope::new(data).read::<OpenTypeFont<'_>>().unwrap();
+        font_file.table_provider(0).unwrap().has_table(table)
+    }
+
+    /// Subset a face to the glyphs of `text` and check that the subset
+    /// maps every character to a glyph.
+    fn subset_text(font: &FontData, text: &str) -> Vec<u8> {
+        let mut glyphs = vec![0];
+        for ch in text.chars() {
+            let glyph = glyph_for(&font.data, font.face_index(), ch);
+            assert_ne!(glyph, 0, "no glyph for {:?}", ch);
+            if !glyphs.contains(&glyph) {
+                glyphs.push(glyph);
+            }
+        }
+        let subset = AllsortsSubsetter::new().subset(font, &glyphs).unwrap();
+        for ch in text.chars() {
+            assert_ne!(glyph_for(&subset, 0, ch), 0, "subset lost {:?}", ch);
+        }
+        subset
+    }
+
+    #[test]
+    fn test_subset_maps_code_points() {
+        let Some(font) = system_font(false) else {
+            // No TrueType font installed
+            return;
+        };
+        let subset = subset_text(&font, "Hello, wörld");
+        assert!(subset.len() < font.data.len());
+        assert_eq!(
+            AllsortsSubsetter::new().whole_face(&font).unwrap(),
+            font.data
+        );
+    }
+
+    #[test]
+    fn test_collection_member_is_subset() {
+        let (Some(regular), Some(bold)) = (system_font(false), system_font(true)) else {
+            return;
+        };
+        if regular.data == bold.data {
+            // No bold face installed
+            return;
+        }
+        let collection = write_fonts(&[read_tables(&regular.data), read_tables(&bold.data)]);
+        assert!(!head(&collection, 0).is_bold());
+        assert!(head(&collection, 1).is_bold());
+
+        let member = font_data(collection, 1);
+        let subset = subset_text(&member, "Bold");
+        assert!(head(&subset, 0).is_bold());
+
+        let face = AllsortsSubsetter::new().whole_face(&member).unwrap();
+        assert_ne!(&face[..4], b"ttcf");
+        assert!(head(&face, 0).is_bold());
+        assert_eq!(glyph_for(&face, 0, 'B'), glyph_for(&bold.data, 0, 'B'));
+    }
+
+    #[test]
+    fn test_variable_font_is_instanced() {
+        let Some(font) = system_font(false) else {
+            return;
+        };
+        let mut tables = read_tables(&font.data);
+        let maxp = tables.iter().find(|(tag, _)| tag == b"maxp").unwrap();
+        let glyph_count = u16::from_be_bytes([maxp.1[4], maxp.1[5]]);
+
+        // One weight axis from 100 to 900 with a Bold instance
+        let mut fvar = Vec::new();
+        for value in [1u16, 0, 16, 2, 1, 20, 1, 8] {
+            fvar.extend_from_slice(&value.to_be_bytes());
+        }
+        fvar.extend_from_slice(b"wght");
+        for value in [100i32, 400, 900] {
+            fvar.extend_from_slice(&(value << 16).to_be_bytes());
+        }
+        fvar.extend_from_slice(&[0, 0, 1, 0]);
+        fvar.extend_from_slice(&[0, 2, 0, 0]);
+        fvar.extend_from_slice(&(700i32 << 16).to_be_bytes());
+
+        // Glyph variations without any deltas
+        let data_offset = 20 + (glyph_count as u32 + 1) * 2;
+        let mut gvar = Vec::new();
+        gvar.extend_from_slice(&[0, 1, 0, 0, 0, 1, 0, 0]);
+        gvar.extend_from_slice(&data_offset.to_be_bytes());
+        gvar.extend_from_slice(&glyph_count.to_be_bytes());
+        gvar.extend_from_slice(&[0, 0]);
+        gvar.extend_from_slice(&data_offset.to_be_bytes());
+        gvar.resize(data_offset as usize, 0);
+
+        tables.push((*b"fvar", fvar));
+        tables.push((*b"gvar", gvar));
+        let variable = write_fonts(&[tables]);
+
+        let default = font_data(variable.clone(), 0);
+        let subset = subset_text(&default, "Variable");
+        assert!(!has_table(&subset, tag::FVAR));
+        assert!(!has_table(&subset, tag::GVAR));
+        assert!(!head(&subset, 0).is_bold());
+
+        // fontconfig selects the first named instance with index 1 << 16
+        let named = font_data(variable, 1 << 16);
+        assert_eq!(named.face_index(), 0);
+        assert_eq!(named.named_instance(), Some(0));
+        let subset = subset_text(&named, "Variable");
+        assert!(head(&subset, 0).is_bold());
+
+        let face = AllsortsSubsetter::new().whole_face(&named).unwrap();
+        assert!(!has_table(&face, tag::FVAR));
+        assert_eq!(glyph_for(&face, 0, 'V'), glyph_for(&font.data, 0, 'V'));
+    }
+}
@@ -3,6 +3,7 @@ use std::borrow::Cow;
 
 /// Math node representing a single element in the formula AST
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MathNode<'a> {
     /// Plain text or identifier
     Text(Cow<'a, str>),
@@ -2,6 +2,7 @@ use std::borrow::Cow;
 
 /// Mathematical operators
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operator {
     // Basic arithmetic
     Plus,
@@ -103,6 +104,7 @@ pub enum Operator {
 
 /// Mathematical symbols (Greek letters, special symbols, etc.)
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Symbol<'a> {
     pub name: Cow<'a, str>,
     pub unicode: Option<char>,
@@ -111,6 +113,7 @@ pub struct Symbol<'a> {
 
 /// Function names for mathematical functions
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FunctionName {
     Sin,
     Cos,
@@ -153,6 +156,7 @@ pub enum FunctionName {
 
 /// Predefined symbols for common mathematical entities
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PredefinedSymbol {
     // Greek letters
     Alpha,
@@ -220,6 +224,7 @@ pub enum PredefinedSymbol {
 
 /// Fence types for fenced expressions
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Fence {
     Paren,         // ( )
     Bracket,       // [ ]
@@ -237,6 +242,7 @@ pub enum Fence {
 
 /// Fence character specification for customizable fences
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FenceSpec {
     pub open: Option<String>,
     pub close: Option<String>,
@@ -244,6 +250,7 @@ pub struct FenceSpec {
 
 /// Large operators (sum, product, integral, etc.)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LargeOperator {
     Sum,             // ∑
     Product,         // ∏
@@ -269,6 +276,7 @@ pub enum LargeOperator {
 
 /// Matrix fence types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MatrixFence {
     None,       // No fence
     Paren,      // ( )
@@ -280,6 +288,7 @@ pub enum MatrixFence {
 
 /// Accent types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum AccentType {
     Hat,       // ^
     Check,     // ˇ
@@ -296,6 +305,7 @@ pub enum AccentType {
 
 /// Space types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SpaceType {
     Thin,     // Thin space
     Medium,   // Medium space
@@ -307,6 +317,7 @@ pub enum SpaceType {
 
 /// Style types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StyleType {
     Normal,
     Bold,
@@ -326,6 +337,7 @@ pub enum StyleType {
 
 /// Alignment types for positioning elements
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Alignment {
     Left,
     Center,
@@ -340,6 +352,7 @@ pub enum Alignment {
 
 /// Vertical alignment types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum VerticalAlignment {
     Top,
     Bottom,
@@ -350,6 +363,7 @@ pub enum VerticalAlignment {
 
 /// Position types for scripts and accents
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Position {
     Prefix,
     Postfix,
@@ -360,6 +374,7 @@ pub enum Position {
 
 /// Fraction types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum FractionType {
     Bar,    // Normal fraction bar
     NoBar,  // Linear fraction (no bar)
@@ -368,6 +383,7 @@ pub enum FractionType {
 
 /// Math variant types (equivalent to style types but OMML-specific)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MathVariant {
     Normal,
     Bold,
@@ -387,6 +403,7 @@ pub enum MathVariant {
 
 /// Shape types for delimiters
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShapeType {
     Centered,
     Match,
@@ -394,6 +411,7 @@ pub enum ShapeType {
 
 /// Break types for line breaking
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BreakType {
     None,
     Line,
@@ -402,6 +420,7 @@ pub enum BreakType {
 
 /// Underline/overline styles
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LineStyle {
     Single,
     Double,
@@ -413,6 +432,7 @@ pub enum LineStyle {
 
 /// Strike-through styles
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StrikeStyle {
     Single,
     Double,
@@ -420,6 +440,7 @@ pub enum StrikeStyle {
 
 /// Border box properties
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BorderBoxStyle {
     pub hide_top: bool,
     pub hide_bottom: bool,
@@ -433,6 +454,7 @@ pub struct BorderBoxStyle {
 
 /// Equation array properties
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EqArrayProperties {
     pub base_alignment: Option<Alignment>,
     pub max_distance: Option<f32>,
@@ -443,6 +465,7 @@ pub struct EqArrayProperties {
 
 /// Matrix properties
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MatrixProperties {
     pub base_alignment: Option<Alignment>,
     pub column_gap: Option<f32>,
@@ -452,6 +475,7 @@ pub struct MatrixProperties {
 
 /// Types of limits in mathematical expressions
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum LimitType {
     Lower,
     Upper,
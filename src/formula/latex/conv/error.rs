@@ -1,10 +1,20 @@
-// Error definitions for LaTeX conversion
+// Error definitions for LaTeX conversion and parsing
 
-/// Errors that can occur during LaTeX conversion
+/// Errors that can occur during LaTeX conversion and parsing
 #[derive(Debug)]
 pub enum LatexError {
     FormatError(String),
     InvalidNode(String),
+    /// Malformed LaTeX input; `offset` is the byte offset of the problem
+    Syntax {
+        message: String,
+        offset: usize,
+    },
+    /// A command or environment the parser does not support
+    Unsupported {
+        command: String,
+        offset: usize,
+    },
 }
 
 impl std::fmt::Display for LatexError {
@@ -12,6 +22,12 @@ impl std::fmt::Display for LatexError {
         match self {
             LatexError::FormatError(msg) => write!(f, "Format error: {}", msg),
             LatexError::InvalidNode(msg) => write!(f, "Invalid node: {}", msg),
+            LatexError::Syntax { message, offset } => {
+                write!(f, "Syntax error at offset {}: {}", offset, message)
+            },
+            LatexError::Unsupported { command, offset } => {
+                write!(f, "Unsupported command '{}' at offset {}", command, offset)
+            },
         }
     }
 }
@@ -31,6 +47,18 @@ mod tests {
         let err = LatexError::InvalidNode("test node".to_string());
         assert!(err.to_string().contains("Invalid node"));
         assert!(err.to_string().contains("test node"));
+
+        let err = LatexError::Syntax {
+            message: "Unmatched '}'".to_string(),
+            offset: 3,
+        };
+        assert_eq!(err.to_string(), "Syntax error at offset 3: Unmatched '}'");
+
+        let err = LatexError::Unsupported {
+            command: "\\foo".to_string(),
+            offset: 0,
+        };
+        assert_eq!(err.to_string(), "Unsupported command '\\foo' at offset 0");
     }
 
     #[test]
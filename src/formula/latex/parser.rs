@@ -0,0 +1,1304 @@
+// LaTeX parser
+//
+// This module parses LaTeX math into the formula AST, so formulas authored
+// in LaTeX can be converted to other formats. It covers the commonly used
+// subset of LaTeX math: fractions, scripts, radicals, accents, large
+// operators with limits, fences, matrices, Greek letters, font styles and
+// text. Anything else is rejected with an error naming the command and its
+// position.
+
+use super::LatexError;
+use super::operators::is_standard_function;
+use crate::formula::ast::{
+    AccentType, Fence, LargeOperator, MathNode, MatrixFence, Operator, Position, PredefinedSymbol,
+    SpaceType, StyleType, Symbol,
+};
+use std::borrow::Cow;
+
+/// Maximum nesting of groups, fences and environments
+const MAX_DEPTH: usize = 128;
+
+/// LaTeX parser producing arena-allocated formula nodes
+///
+/// # Example
+/// ```ignore
+/// let formula = Formula::new();
+/// let parser = LatexParser::new(formula.arena());
+/// let nodes = parser.parse(r"\frac{a}{b} + \sqrt{x^2}")?;
+/// ```
+pub struct LatexParser<'arena> {
+    arena: &'arena bumpalo::Bump,
+}
+
+impl<'arena> LatexParser<'arena> {
+    /// Create a new LaTeX parser
+    pub fn new(arena: &'arena bumpalo::Bump) -> Self {
+        Self { arena }
+    }
+
+    /// Parse LaTeX math (without `$` delimiters) into formula nodes
+    ///
+    /// Returns [`LatexError::Syntax`] for malformed input and
+    /// [`LatexError::Unsupported`] for commands and environments outside the
+    /// supported subset.
+    pub fn parse(&self, latex: &str) -> Result<Vec<MathNode<'arena>>, LatexError> {
+        let mut cursor = Cursor {
+            arena: self.arena,
+            input: latex,
+            pos: 0,
+            depth: 0,
+        };
+        cursor.parse_formula()
+    }
+}
+
+/// Parsing state over one input string
+struct Cursor<'arena, 's> {
+    arena: &'arena bumpalo::Bump,
+    input: &'s str,
+    pos: usize,
+    depth: usize,
+}
+
+impl<'arena, 's> Cursor<'arena, 's> {
+    /// Parse the whole input
+    fn parse_formula(&mut self) -> Result<Vec<MathNode<'arena>>, LatexError> {
+        let mut nodes = Vec::new();
+        loop {
+            self.parse_sequence(&mut nodes)?;
+            if self.pos >= self.input.len() {
+                return Ok(nodes);
+            }
+            if self.eat_str("\\\\") {
+                nodes.push(MathNode::LineBreak);
+                continue;
+            }
+            return Err(self.unexpected());
+        }
+    }
+
+    /// Parse terms until the end of input or a token that closes an
+    /// enclosing construct (`}`, `&`, `\\`, `\right`, `\end`).
+    fn parse_sequence(&mut self, nodes: &mut Vec<MathNode<'arena>>) -> Result<(), LatexError> {
+        loop {
+            self.skip_whitespace();
+            if self.at_stop() {
+                return Ok(());
+            }
+            self.parse_term(nodes)?;
+        }
+    }
+
+    /// Parse one atom with its scripts, plus the operand of a large operator.
+    fn parse_term(&mut self, nodes: &mut Vec<MathNode<'arena>>) -> Result<(), LatexError> {
+        let Some(atom) = self.parse_atom(false)? else {
+            return Ok(());
+        };
+        let node = self.parse_scripts(atom)?;
+        match node {
+            MathNode::LargeOp {
+                operator,
+                lower_limit,
+                upper_limit,
+                integrand: None,
+                hide_lower,
+                hide_upper,
+            } => {
+                let operand = self.parse_operand()?;
+                nodes.push(MathNode::LargeOp {
+                    operator,
+                    lower_limit,
+                    upper_limit,
+                    integrand: (!operand.is_empty()).then_some(operand),
+                    hide_lower,
+                    hide_upper,
+                });
+            },
+            // Plain groups only matter for scripts
+            MathNode::Row(content) => nodes.extend(content),
+            node => nodes.push(node),
+        }
+        Ok(())
+    }
+
+    /// Parse the operand of a large operator: the terms up to the next
+    /// relation, `+`/`-` or `,` at this level.
+    fn parse_operand(&mut self) -> Result<Vec<MathNode<'arena>>, LatexError> {
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.at_stop() || self.at_operand_end() {
+                return Ok(nodes);
+            }
+            self.parse_term(&mut nodes)?;
+        }
+    }
+
+    /// Attach any `^` and `_` scripts following `base`.
+    fn parse_scripts(&mut self, base: MathNode<'arena>) -> Result<MathNode<'arena>, LatexError> {
+        let mut subscript = None;
+        let mut superscript = None;
+        loop {
+            self.skip_whitespace();
+            let start = self.pos;
+            if self.eat_command("limits") || self.eat_command("nolimits") {
+                continue;
+            }
+            let script = match self.peek() {
+                Some('^') => &mut superscript,
+                Some('_') => &mut subscript,
+                _ => break,
+            };
+            if script.is_some() {
+                return Err(self.syntax(start, "Double superscript or subscript"));
+            }
+            self.pos += 1;
+            *script = Some(self.parse_argument()?);
+        }
+
+        if subscript.is_none() && superscript.is_none() {
+            return Ok(base);
+        }
+        let node = match base {
+            MathNode::LargeOp {
+                operator,
+                hide_lower,
+                hide_upper,
+                ..
+            } => MathNode::LargeOp {
+                operator,
+                lower_limit: subscript,
+                upper_limit: superscript,
+                integrand: None,
+                hide_lower,
+                hide_upper,
+            },
+            base => {
+                let base = match base {
+                    MathNode::Row(content) => content,
+                    base => vec![base],
+                };
+                match (subscript, superscript) {
+                    (Some(subscript), Some(superscript)) => MathNode::SubSup {
+                        base,
+                        subscript,
+                        superscript,
+                    },
+                    (Some(subscript), None) => MathNode::Sub { base, subscript },
+                    (None, Some(exponent)) => MathNode::Power { base, exponent },
+                    (None, None) => unreachable!(),
+                }
+            },
+        };
+        Ok(node)
+    }
+
+    /// Parse a command argument or script: a `{...}` group or a single token.
+    fn parse_argument(&mut self) -> Result<Vec<MathNode<'arena>>, LatexError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if self.at_stop() || matches!(self.peek(), Some('^' | '_')) {
+            return Err(self.syntax(start, "Missing argument"));
+        }
+        Ok(match self.parse_atom(true)? {
+            Some(MathNode::Row(content)) => content,
+            Some(node) => vec![node],
+            None => Vec::new(),
+        })
+    }
+
+    /// Parse a single atom. With `single`, a number is one digit, as in
+    /// `x^12` meaning `x^{1}2`.
+    fn parse_atom(&mut self, single: bool) -> Result<Option<MathNode<'arena>>, LatexError> {
+        let start = self.pos;
+        let Some(c) = self.peek() else {
+            return Err(self.syntax(start, "Unexpected end of formula"));
+        };
+        let node = match c {
+            '{' => {
+                self.pos += 1;
+                MathNode::Row(self.parse_group(start)?)
+            },
+            '\\' => return self.parse_command(),
+            '0'..='9' | '.' if self.is_number_start() => self.parse_number(single),
+            '+' => self.operator(Operator::Plus),
+            '-' => self.operator(Operator::Minus),
+            '=' => self.operator(Operator::Equals),
+            '<' => self.operator(Operator::LessThan),
+            '>' => self.operator(Operator::GreaterThan),
+            '\'' => {
+                let count = self.input[start..]
+                    .chars()
+                    .take_while(|&c| c == '\'')
+                    .count();
+                self.pos += count;
+                MathNode::Operator(match count {
+                    1 => Operator::Prime,
+                    2 => Operator::DoublePrime,
+                    _ => Operator::TriplePrime,
+                })
+            },
+            '~' => {
+                self.pos += 1;
+                MathNode::Space(SpaceType::Medium)
+            },
+            // A script without a base
+            '^' | '_' => MathNode::Row(Vec::new()),
+            '%' => {
+                self.pos = self.input[start..]
+                    .find('\n')
+                    .map_or(self.input.len(), |end| start + end + 1);
+                return Ok(None);
+            },
+            '}' | '&' | '$' | '#' => return Err(self.unexpected()),
+            c => {
+                self.pos += c.len_utf8();
+                self.text(&self.input[start..self.pos])
+            },
+        };
+        Ok(Some(node))
+    }
+
+    /// Parse the rest of a `{...}` group opened at `open`.
+    fn parse_group(&mut self, open: usize) -> Result<Vec<MathNode<'arena>>, LatexError> {
+        self.enter(open)?;
+        let mut nodes = Vec::new();
+        loop {
+            self.parse_sequence(&mut nodes)?;
+            if self.eat('}') {
+                break;
+            }
+            if self.eat_str("\\\\") {
+                nodes.push(MathNode::LineBreak);
+                continue;
+            }
+            if self.pos >= self.input.len() {
+                return Err(self.syntax(open, "Missing '}' for this '{'"));
+            }
+            return Err(self.unexpected());
+        }
+        self.depth -= 1;
+        Ok(nodes)
+    }
+
+    /// Parse a number such as `42` or `3.14`.
+    fn parse_number(&mut self, single: bool) -> MathNode<'arena> {
+        let start = self.pos;
+        if single {
+            self.pos += 1;
+        } else {
+            let mut seen_dot = false;
+            for (i, c) in self.input[start..].char_indices() {
+                let is_digit = c.is_ascii_digit();
+                let is_decimal_point = c == '.'
+                    && !seen_dot
+                    && self.input[start + i + 1..].starts_with(|c: char| c.is_ascii_digit());
+                if !is_digit && !is_decimal_point {
+                    break;
+                }
+                seen_dot |= is_decimal_point;
+                self.pos = start + i + 1;
+            }
+        }
+        MathNode::Number(Cow::Borrowed(
+            self.arena.alloc_str(&self.input[start..self.pos]),
+        ))
+    }
+
+    /// Parse a command starting with a backslash.
+    fn parse_command(&mut self) -> Result<Option<MathNode<'arena>>, LatexError> {
+        let start = self.pos;
+        self.pos += 1;
+        let name = self.read_command_name();
+        if name.is_empty() {
+            return Err(self.syntax(start, "Missing command name after '\\'"));
+        }
+
+        if let Some(symbol) = predefined_symbol(name) {
+            return Ok(Some(MathNode::PredefinedSymbol(symbol)));
+        }
+        if let Some(operator) = operator(name) {
+            return Ok(Some(MathNode::Operator(operator)));
+        }
+        if let Some(unicode) = symbol_char(name) {
+            return Ok(Some(MathNode::Symbol(Symbol {
+                name: Cow::Borrowed(self.arena.alloc_str(name)),
+                unicode: Some(unicode),
+                variant: None,
+            })));
+        }
+        if let Some(operator) = large_operator(name) {
+            return Ok(Some(MathNode::LargeOp {
+                operator,
+                lower_limit: None,
+                upper_limit: None,
+                integrand: None,
+                hide_lower: false,
+                hide_upper: false,
+            }));
+        }
+        if let Some(space) = space(name) {
+            return Ok(Some(MathNode::Space(space)));
+        }
+        if let Some(accent) = accent(name) {
+            let base = self.parse_argument()?;
+            return Ok(Some(MathNode::Accent {
+                base: Box::new(base),
+                accent,
+                position: None,
+            }));
+        }
+        if let Some(style) = style(name) {
+            let content = self.parse_argument()?;
+            return Ok(Some(MathNode::Style { style, content }));
+        }
+        if let Some(style) = text_style(name) {
+            let text = self.parse_text_argument()?;
+            return Ok(Some(MathNode::Run {
+                content: vec![self.text(text)],
+                literal: Some(true),
+                style,
+                font: None,
+                color: None,
+                underline: None,
+                overline: None,
+                strike_through: None,
+                double_strike_through: None,
+            }));
+        }
+        if is_standard_function(name) {
+            let name = self.arena.alloc_str(name);
+            return self.parse_function(name).map(Some);
+        }
+
+        let node = match name {
+            // Escaped characters
+            "{" | "}" | "%" | "$" | "&" | "#" | "_" => self.text(name),
+            "|" => self.text("‖"),
+            "\\" => return Err(self.syntax(start, "Unexpected line break")),
+            // Commands that only affect spacing or limit placement
+            "displaystyle" | "textstyle" | "scriptstyle" | "scriptscriptstyle" | "limits"
+            | "nolimits" | "middle" => return Ok(None),
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                MathNode::Frac {
+                    numerator,
+                    denominator,
+                    line_thickness: None,
+                    frac_type: None,
+                }
+            },
+            "binom" | "dbinom" | "tbinom" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                MathNode::Fenced {
+                    open: Fence::Paren,
+                    content: vec![MathNode::Frac {
+                        numerator,
+                        denominator,
+                        line_thickness: Some(0.0),
+                        frac_type: Some(crate::formula::ast::FractionType::NoBar),
+                    }],
+                    close: Fence::Paren,
+                    separator: None,
+                }
+            },
+            "sqrt" => {
+                self.skip_whitespace();
+                let index = if self.eat('[') {
+                    Some(self.parse_optional_argument()?)
+                } else {
+                    None
+                };
+                let base = self.parse_argument()?;
+                MathNode::Root { base, index }
+            },
+            "overline" | "underline" => MathNode::Bar {
+                base: Box::new(self.parse_argument()?),
+                position: Some(if name == "overline" {
+                    Position::Top
+                } else {
+                    Position::Bottom
+                }),
+            },
+            "overbrace" | "underbrace" => MathNode::GroupChar {
+                base: Box::new(self.parse_argument()?),
+                character: None,
+                position: Some(if name == "overbrace" {
+                    Position::Top
+                } else {
+                    Position::Bottom
+                }),
+                vertical_alignment: None,
+            },
+            "overset" | "stackrel" => {
+                let over = self.parse_argument()?;
+                let base = self.parse_argument()?;
+                MathNode::Over {
+                    base,
+                    over,
+                    position: None,
+                }
+            },
+            "underset" => {
+                let under = self.parse_argument()?;
+                let base = self.parse_argument()?;
+                MathNode::Under {
+                    base,
+                    under,
+                    position: None,
+                }
+            },
+            "boxed" => MathNode::BorderBox {
+                content: Box::new(self.parse_argument()?),
+                style: None,
+            },
+            "phantom" => MathNode::Phantom(Box::new(self.parse_argument()?)),
+            "operatorname" => {
+                self.eat('*');
+                let name = self.parse_text_argument()?;
+                let name = self.arena.alloc_str(name.trim());
+                return self.parse_function(name).map(Some);
+            },
+            "not" => {
+                self.skip_whitespace();
+                if self.eat('=') {
+                    MathNode::Operator(Operator::NotEquals)
+                } else if self.eat_command("in") {
+                    MathNode::Operator(Operator::NotIn)
+                } else {
+                    return Err(self.unsupported(start, "\\not"));
+                }
+            },
+            "left" => self.parse_fenced(start)?,
+            "begin" => self.parse_environment(start)?,
+            "right" => return Err(self.syntax(start, "\\right without matching \\left")),
+            "end" => return Err(self.syntax(start, "\\end without matching \\begin")),
+            _ => return Err(self.unsupported(start, &format!("\\{}", name))),
+        };
+        Ok(Some(node))
+    }
+
+    /// Parse a named function such as `\sin` and its argument.
+    fn parse_function(&mut self, name: &'arena str) -> Result<MathNode<'arena>, LatexError> {
+        // Scripts on the name (`\sin^2 x`) apply to the whole function
+        let scripts = self.parse_scripts(MathNode::Row(Vec::new()))?;
+        let argument = self.parse_operand_term()?;
+        let function = MathNode::Function {
+            name: Cow::Borrowed(name),
+            argument,
+        };
+        Ok(match scripts {
+            MathNode::Power { exponent, .. } => MathNode::Power {
+                base: vec![function],
+                exponent,
+            },
+            MathNode::Sub { subscript, .. } => MathNode::Sub {
+                base: vec![function],
+                subscript,
+            },
+            MathNode::SubSup {
+                subscript,
+                superscript,
+                ..
+            } => MathNode::SubSup {
+                base: vec![function],
+                subscript,
+                superscript,
+            },
+            _ => function,
+        })
+    }
+
+    /// Parse the single term a function applies to, if any.
+    fn parse_operand_term(&mut self) -> Result<Vec<MathNode<'arena>>, LatexError> {
+        let mut nodes = Vec::new();
+        self.skip_whitespace();
+        if !self.at_stop() && !self.at_operand_end() {
+            self.parse_term(&mut nodes)?;
+        }
+        Ok(nodes)
+    }
+
+    /// Parse `\left<delim> ... \right<delim>` after the `\left`.
+    fn parse_fenced(&mut self, start: usize) -> Result<MathNode<'arena>, LatexError> {
+        self.enter(start)?;
+        let open = self.parse_delimiter()?;
+        let mut content = Vec::new();
+        loop {
+            self.parse_sequence(&mut content)?;
+            if self.eat_command("right") {
+                break;
+            }
+            if self.eat_str("\\\\") {
+                content.push(MathNode::LineBreak);
+                continue;
+            }
+            if self.pos >= self.input.len() {
+                return Err(self.syntax(start, "Missing \\right for this \\left"));
+            }
+            return Err(self.unexpected());
+        }
+        let close = self.parse_delimiter()?;
+        self.depth -= 1;
+        Ok(MathNode::Fenced {
+            open,
+            content,
+            close,
+            separator: None,
+        })
+    }
+
+    /// Parse the delimiter following `\left` or `\right`.
+    fn parse_delimiter(&mut self) -> Result<Fence, LatexError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let fence = if self.eat('\\') {
+            match self.read_command_name() {
+                "{" | "}" | "lbrace" | "rbrace" => Some(Fence::Brace),
+                "|" | "Vert" | "lVert" | "rVert" => Some(Fence::DoublePipe),
+                "vert" | "lvert" | "rvert" => Some(Fence::Pipe),
+                "lbrack" | "rbrack" => Some(Fence::Bracket),
+                "langle" | "rangle" => Some(Fence::Angle),
+                "lfloor" | "rfloor" => Some(Fence::Floor),
+                "lceil" | "rceil" => Some(Fence::Ceiling),
+                _ => None,
+            }
+        } else {
+            let fence = match self.peek() {
+                Some('(' | ')') => Some(Fence::Paren),
+                Some('[' | ']') => Some(Fence::Bracket),
+                Some('|') => Some(Fence::Pipe),
+                Some('<' | '>') => Some(Fence::Angle),
+                Some('.') => Some(Fence::None),
+                _ => None,
+            };
+            if fence.is_some() {
+                self.pos += 1;
+            }
+            fence
+        };
+        fence.ok_or_else(|| self.syntax(start, "Missing or invalid delimiter"))
+    }
+
+    /// Parse `\begin{env} ... \end{env}` after the `\begin`.
+    fn parse_environment(&mut self, start: usize) -> Result<MathNode<'arena>, LatexError> {
+        let env = self.parse_environment_name()?;
+        let fence = match env {
+            "matrix" | "smallmatrix" => MatrixFence::None,
+            "pmatrix" => MatrixFence::Paren,
+            "bmatrix" => MatrixFence::Bracket,
+            "Bmatrix" => MatrixFence::Brace,
+            "vmatrix" => MatrixFence::Pipe,
+            "Vmatrix" => MatrixFence::DoublePipe,
+            "cases" => MatrixFence::None,
+            _ => return Err(self.unsupported(start, &format!("\\begin{{{}}}", env))),
+        };
+
+        self.enter(start)?;
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        loop {
+            let mut cell = Vec::new();
+            self.parse_sequence(&mut cell)?;
+            row.push(cell);
+            if self.eat('&') {
+                continue;
+            }
+            if self.eat_str("\\\\") {
+                rows.push(std::mem::take(&mut row));
+                continue;
+            }
+            let end = self.pos;
+            if self.eat_command("end") {
+                let end_env = self.parse_environment_name()?;
+                if end_env != env {
+                    return Err(self.syntax(
+                        end,
+                        &format!("Expected \\end{{{}}}, found \\end{{{}}}", env, end_env),
+                    ));
+                }
+                // A trailing `\\` leaves an empty last row
+                if !(row.len() == 1 && row[0].is_empty()) {
+                    rows.push(row);
+                }
+                break;
+            }
+            if self.pos >= self.input.len() {
+                return Err(self.syntax(start, &format!("Missing \\end{{{}}}", env)));
+            }
+            return Err(self.unexpected());
+        }
+        self.depth -= 1;
+
+        let matrix = MathNode::Matrix {
+            rows,
+            fence_type: fence,
+            properties: None,
+        };
+        Ok(if env == "cases" {
+            MathNode::Fenced {
+                open: Fence::Brace,
+                content: vec![matrix],
+                close: Fence::None,
+                separator: None,
+            }
+        } else {
+            matrix
+        })
+    }
+
+    /// Parse the `{name}` of an environment.
+    fn parse_environment_name(&mut self) -> Result<&'s str, LatexError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if !self.eat('{') {
+            return Err(self.syntax(start, "Missing environment name"));
+        }
+        let input = self.input;
+        let name_start = self.pos;
+        let len = input[name_start..]
+            .find(|c: char| !c.is_ascii_alphabetic() && c != '*')
+            .unwrap_or(input.len() - name_start);
+        self.pos += len;
+        if len == 0 || !self.eat('}') {
+            return Err(self.syntax(start, "Invalid environment name"));
+        }
+        Ok(&input[name_start..name_start + len])
+    }
+
+    /// Parse the `[...]` optional argument after its opening bracket.
+    fn parse_optional_argument(&mut self) -> Result<Vec<MathNode<'arena>>, LatexError> {
+        let open = self.pos - 1;
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.eat(']') {
+                return Ok(nodes);
+            }
+            if self.at_stop() {
+                return Err(self.syntax(open, "Missing ']' for this '['"));
+            }
+            self.parse_term(&mut nodes)?;
+        }
+    }
+
+    /// Read the raw `{...}` argument of a text command.
+    fn parse_text_argument(&mut self) -> Result<&'s str, LatexError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if !self.eat('{') {
+            return Err(self.syntax(start, "Expected '{' after text command"));
+        }
+        let input = self.input;
+        let mut nesting = 0usize;
+        for (i, c) in input[self.pos..].char_indices() {
+            match c {
+                '{' => nesting += 1,
+                '}' if nesting == 0 => {
+                    let text = &input[self.pos..self.pos + i];
+                    self.pos += i + 1;
+                    return Ok(text);
+                },
+                '}' => nesting -= 1,
+                _ => {},
+            }
+        }
+        Err(self.syntax(start, "Missing '}' for this '{'"))
+    }
+
+    /// Read the name after a backslash: a run of letters, or one other character.
+    fn read_command_name(&mut self) -> &'s str {
+        let input = self.input;
+        let rest = &input[self.pos..];
+        let len = match rest.find(|c: char| !c.is_ascii_alphabetic()) {
+            Some(0) => rest.chars().next().map_or(0, char::len_utf8),
+            Some(len) => len,
+            None => rest.len(),
+        };
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Check for a token that ends the current sequence.
+    fn at_stop(&self) -> bool {
+        match self.peek() {
+            None | Some('}' | '&') => true,
+            Some('\\') => {
+                self.rest().starts_with("\\\\")
+                    || self.at_command("right")
+                    || self.at_command("end")
+            },
+            _ => false,
+        }
+    }
+
+    /// Check for a token that ends the operand of a large operator.
+    fn at_operand_end(&self) -> bool {
+        const RELATIONS: [&str; 22] = [
+            "pm",
+            "mp",
+            "leq",
+            "le",
+            "geq",
+            "ge",
+            "neq",
+            "ne",
+            "approx",
+            "equiv",
+            "sim",
+            "simeq",
+            "cong",
+            "propto",
+            "to",
+            "rightarrow",
+            "leftarrow",
+            "implies",
+            "iff",
+            "quad",
+            "qquad",
+            "not",
+        ];
+        match self.peek() {
+            Some('+' | '-' | '=' | '<' | '>' | ',' | ';' | ']') => true,
+            Some('\\') => RELATIONS.iter().any(|name| self.at_command(name)),
+            _ => false,
+        }
+    }
+
+    /// Check whether the input continues with `\name` as a whole command.
+    fn at_command(&self, name: &str) -> bool {
+        self.rest()
+            .strip_prefix('\\')
+            .and_then(|rest| rest.strip_prefix(name))
+            .is_some_and(|after| !after.starts_with(|c: char| c.is_ascii_alphabetic()))
+    }
+
+    fn eat_command(&mut self, name: &str) -> bool {
+        let found = self.at_command(name);
+        if found {
+            self.pos += name.len() + 1;
+        }
+        found
+    }
+
+    fn is_number_start(&self) -> bool {
+        let mut chars = self.rest().chars();
+        match chars.next() {
+            Some('.') => chars.next().is_some_and(|c| c.is_ascii_digit()),
+            Some(c) => c.is_ascii_digit(),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn rest(&self) -> &'s str {
+        &self.input[self.pos..]
+    }
+
+    #[inline]
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += c.len_utf8();
+        }
+        found
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let found = self.rest().starts_with(s);
+        if found {
+            self.pos += s.len();
+        }
+        found
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    /// Track nesting to keep recursion bounded on hostile input.
+    fn enter(&mut self, start: usize) -> Result<(), LatexError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.syntax(start, "Formula is nested too deeply"));
+        }
+        Ok(())
+    }
+
+    fn operator(&mut self, operator: Operator) -> MathNode<'arena> {
+        self.pos += 1;
+        MathNode::Operator(operator)
+    }
+
+    fn text(&self, text: &str) -> MathNode<'arena> {
+        MathNode::Text(Cow::Borrowed(self.arena.alloc_str(text)))
+    }
+
+    fn syntax(&self, offset: usize, message: &str) -> LatexError {
+        LatexError::Syntax {
+            message: message.to_string(),
+            offset,
+        }
+    }
+
+    fn unsupported(&self, offset: usize, command: &str) -> LatexError {
+        LatexError::Unsupported {
+            command: command.to_string(),
+            offset,
+        }
+    }
+
+    /// Error for a closing token that does not belong at the current position.
+    fn unexpected(&self) -> LatexError {
+        let message = match self.peek() {
+            Some('}') => "Unmatched '}'".to_string(),
+            Some('&') => "'&' outside of a matrix".to_string(),
+            Some('\\') if self.at_command("right") => "\\right without matching \\left".to_string(),
+            Some('\\') if self.at_command("end") => "\\end without matching \\begin".to_string(),
+            Some('\\') => "Unexpected line break".to_string(),
+            Some(c) => format!("Unexpected '{}'", c),
+            None => "Unexpected end of formula".to_string(),
+        };
+        self.syntax(self.pos, &message)
+    }
+}
+
+/// Greek letters and constants with a dedicated node
+fn predefined_symbol(name: &str) -> Option<PredefinedSymbol> {
+    Some(match name {
+        "alpha" => PredefinedSymbol::Alpha,
+        "beta" => PredefinedSymbol::Beta,
+        "gamma" => PredefinedSymbol::Gamma,
+        "delta" => PredefinedSymbol::Delta,
+        "epsilon" => PredefinedSymbol::Epsilon,
+        "zeta" => PredefinedSymbol::Zeta,
+        "eta" => PredefinedSymbol::Eta,
+        "theta" => PredefinedSymbol::Theta,
+        "iota" => PredefinedSymbol::Iota,
+        "kappa" => PredefinedSymbol::Kappa,
+        "lambda" => PredefinedSymbol::Lambda,
+        "mu" => PredefinedSymbol::Mu,
+        "nu" => PredefinedSymbol::Nu,
+        "xi" => PredefinedSymbol::Xi,
+        "pi" => PredefinedSymbol::Pi,
+        "rho" => PredefinedSymbol::Rho,
+        "sigma" => PredefinedSymbol::Sigma,
+        "tau" => PredefinedSymbol::Tau,
+        "upsilon" => PredefinedSymbol::Upsilon,
+        "phi" => PredefinedSymbol::Phi,
+        "chi" => PredefinedSymbol::Chi,
+        "psi" => PredefinedSymbol::Psi,
+        "omega" => PredefinedSymbol::Omega,
+        "Gamma" => PredefinedSymbol::GammaCap,
+        "Delta" => PredefinedSymbol::DeltaCap,
+        "Theta" => PredefinedSymbol::ThetaCap,
+        "Lambda" => PredefinedSymbol::LambdaCap,
+        "Xi" => PredefinedSymbol::XiCap,
+        "Pi" => PredefinedSymbol::PiCap,
+        "Sigma" => PredefinedSymbol::SigmaCap,
+        "Upsilon" => PredefinedSymbol::UpsilonCap,
+        "Phi" => PredefinedSymbol::PhiCap,
+        "Psi" => PredefinedSymbol::PsiCap,
+        "Omega" => PredefinedSymbol::OmegaCap,
+        "aleph" => PredefinedSymbol::Aleph,
+        "infty" => PredefinedSymbol::Infinity,
+        _ => return None,
+    })
+}
+
+/// Operators and relations
+fn operator(name: &str) -> Option<Operator> {
+    Some(match name {
+        "times" => Operator::Times,
+        "cdot" => Operator::Dot,
+        "div" => Operator::Divide,
+        "pm" => Operator::PlusMinus,
+        "mp" => Operator::MinusPlus,
+        "neq" | "ne" => Operator::NotEquals,
+        "leq" | "le" => Operator::LessThanOrEqual,
+        "geq" | "ge" => Operator::GreaterThanOrEqual,
+        "approx" => Operator::Approx,
+        "cong" => Operator::Cong,
+        "equiv" => Operator::Equiv,
+        "propto" => Operator::Propto,
+        "sim" => Operator::Sim,
+        "simeq" => Operator::Simeq,
+        "asymp" => Operator::Asymp,
+        "in" => Operator::In,
+        "notin" => Operator::NotIn,
+        "subset" => Operator::Subset,
+        "supset" => Operator::Superset,
+        "subseteq" => Operator::SubsetEq,
+        "supseteq" => Operator::SupersetEq,
+        "cup" => Operator::Cup,
+        "cap" => Operator::Cap,
+        "wedge" => Operator::Wedge,
+        "vee" => Operator::Vee,
+        "land" => Operator::And,
+        "lor" => Operator::Or,
+        "neg" | "lnot" => Operator::Not,
+        "forall" => Operator::ForAll,
+        "exists" => Operator::Exists,
+        "partial" => Operator::Partial,
+        "nabla" => Operator::Nabla,
+        "emptyset" | "varnothing" => Operator::EmptySet,
+        "ldots" | "dots" => Operator::Ldots,
+        "cdots" => Operator::CDots,
+        "vdots" => Operator::VDots,
+        "ddots" => Operator::DDots,
+        "leftarrow" | "gets" => Operator::LeftArrow,
+        "rightarrow" | "to" => Operator::RightArrow,
+        "uparrow" => Operator::UpArrow,
+        "downarrow" => Operator::DownArrow,
+        "leftrightarrow" => Operator::LeftRightArrow,
+        "updownarrow" => Operator::UpDownArrow,
+        "implies" => Operator::Implies,
+        "iff" => Operator::Iff,
+        "therefore" => Operator::Therefore,
+        "because" => Operator::Because,
+        "angle" => Operator::Angle,
+        "perp" => Operator::Perpendicular,
+        "parallel" => Operator::Parallel,
+        "circ" => Operator::Circ,
+        "bullet" => Operator::Bullet,
+        "ast" => Operator::Star,
+        "prime" => Operator::Prime,
+        "Box" => Operator::Box,
+        "Diamond" => Operator::Diamond,
+        "square" => Operator::Square,
+        _ => return None,
+    })
+}
+
+/// Other symbols, written as their Unicode character
+fn symbol_char(name: &str) -> Option<char> {
+    Some(match name {
+        "varepsilon" => 'ε',
+        "vartheta" => 'ϑ',
+        "varpi" => 'ϖ',
+        "varrho" => 'ϱ',
+        "varsigma" => 'ς',
+        "varphi" => 'φ',
+        "hbar" => 'ℏ',
+        "ell" => 'ℓ',
+        "wp" => '℘',
+        "Re" => 'ℜ',
+        "Im" => 'ℑ',
+        "beth" => 'ℶ',
+        "gimel" => 'ℷ',
+        "oplus" => '⊕',
+        "otimes" => '⊗',
+        "odot" => '⊙',
+        "ll" => '≪',
+        "gg" => '≫',
+        "mapsto" => '↦',
+        "Rightarrow" => '⇒',
+        "Leftarrow" => '⇐',
+        "Leftrightarrow" => '⇔',
+        "nexists" => '∄',
+        "setminus" => '∖',
+        "mid" => '∣',
+        "star" => '⋆',
+        "dagger" => '†',
+        "ddagger" => '‡',
+        "triangle" => '△',
+        "degree" => '°',
+        "langle" => '⟨',
+        "rangle" => '⟩',
+        "lfloor" => '⌊',
+        "rfloor" => '⌋',
+        "lceil" => '⌈',
+        "rceil" => '⌉',
+        "lbrace" => '{',
+        "rbrace" => '}',
+        "vert" => '|',
+        "Vert" => '‖',
+        "colon" => ':',
+        _ => return None,
+    })
+}
+
+/// Operators that take limits
+fn large_operator(name: &str) -> Option<LargeOperator> {
+    Some(match name {
+        "sum" => LargeOperator::Sum,
+        "prod" => LargeOperator::Product,
+        "coprod" => LargeOperator::Coproduct,
+        "int" => LargeOperator::Integral,
+        "iint" => LargeOperator::DoubleIntegral,
+        "iiint" => LargeOperator::TripleIntegral,
+        "oint" => LargeOperator::ContourIntegral,
+        "oiint" => LargeOperator::SurfaceIntegral,
+        "oiiint" => LargeOperator::VolumeIntegral,
+        "bigcup" => LargeOperator::BigUnion,
+        "bigcap" => LargeOperator::BigIntersection,
+        "lim" => LargeOperator::Limit,
+        "max" => LargeOperator::Max,
+        "min" => LargeOperator::Min,
+        "sup" => LargeOperator::Supremum,
+        "inf" => LargeOperator::Infimum,
+        _ => return None,
+    })
+}
+
+fn space(name: &str) -> Option<SpaceType> {
+    Some(match name {
+        "," => SpaceType::Thin,
+        ":" | ">" | " " => SpaceType::Medium,
+        ";" => SpaceType::Thick,
+        "!" => SpaceType::Negative,
+        "quad" => SpaceType::Quad,
+        "qquad" => SpaceType::QQuad,
+        _ => return None,
+    })
+}
+
+fn accent(name: &str) -> Option<AccentType> {
+    Some(match name {
+        "hat" | "widehat" => AccentType::Hat,
+        "check" => AccentType::Check,
+        "tilde" | "widetilde" => AccentType::Tilde,
+        "acute" => AccentType::Acute,
+        "grave" => AccentType::Grave,
+        "dot" => AccentType::Dot,
+        "ddot" => AccentType::DoubleDot,
+        "dddot" => AccentType::TripleDot,
+        "bar" => AccentType::Bar,
+        "breve" => AccentType::Breve,
+        "vec" | "overrightarrow" => AccentType::Vec,
+        _ => return None,
+    })
+}
+
+/// Math font commands
+fn style(name: &str) -> Option<StyleType> {
+    Some(match name {
+        "mathrm" => StyleType::Normal,
+        "mathbf" => StyleType::Bold,
+        "mathit" => StyleType::Italic,
+        "boldsymbol" | "bm" => StyleType::BoldItalic,
+        "mathsf" => StyleType::SansSerif,
+        "mathtt" => StyleType::Monospace,
+        "mathcal" | "mathscr" => StyleType::Script,
+        "mathfrak" => StyleType::Fraktur,
+        "mathbb" => StyleType::DoubleStruck,
+        _ => return None,
+    })
+}
+
+/// Commands whose argument is literal text, with the style they apply
+fn text_style(name: &str) -> Option<Option<StyleType>> {
+    Some(match name {
+        "text" | "textrm" | "textnormal" | "mbox" => None,
+        "textbf" => Some(StyleType::Bold),
+        "textit" => Some(StyleType::Italic),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formula::LatexConverter;
+    use bumpalo::Bump;
+
+    fn parse<'a>(arena: &'a Bump, latex: &str) -> Vec<MathNode<'a>> {
+        LatexParser::new(arena).parse(latex).unwrap()
+    }
+
+    /// Parse and convert back to LaTeX.
+    fn round_trip(latex: &str) -> String {
+        let arena = Bump::new();
+        let nodes = parse(&arena, latex);
+        LatexConverter::new()
+            .convert_nodes(&nodes)
+            .unwrap()
+            .to_string()
+    }
+
+    fn text(s: &str) -> MathNode<'_> {
+        MathNode::Text(Cow::Borrowed(s))
+    }
+
+    #[test]
+    fn test_parse_basic_expression() {
+        let arena = Bump::new();
+        assert_eq!(
+            parse(&arena, "x + 12.5 = y"),
+            vec![
+                text("x"),
+                MathNode::Operator(Operator::Plus),
+                MathNode::Number(Cow::Borrowed("12.5")),
+                MathNode::Operator(Operator::Equals),
+                text("y"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_scripts() {
+        let arena = Bump::new();
+        assert_eq!(
+            parse(&arena, "x^12"),
+            vec![
+                MathNode::Power {
+                    base: vec![text("x")],
+                    exponent: vec![MathNode::Number(Cow::Borrowed("1"))],
+                },
+                MathNode::Number(Cow::Borrowed("2")),
+            ]
+        );
+        assert_eq!(
+            parse(&arena, "a_{i}^{n+1}"),
+            vec![MathNode::SubSup {
+                base: vec![text("a")],
+                subscript: vec![text("i")],
+                superscript: vec![
+                    text("n"),
+                    MathNode::Operator(Operator::Plus),
+                    MathNode::Number(Cow::Borrowed("1")),
+                ],
+            }]
+        );
+        assert_eq!(round_trip("{(a+b)}^2"), "{(a+b)}^{2}");
+    }
+
+    #[test]
+    fn test_parse_fractions_and_radicals() {
+        assert_eq!(round_trip(r"\frac{a}{b}"), r"\frac{a}{b}");
+        assert_eq!(round_trip(r"\frac12"), r"\frac{1}{2}");
+        assert_eq!(round_trip(r"\sqrt{x}"), r"\sqrt{x}");
+        assert_eq!(round_trip(r"\sqrt[3]{x+1}"), r"\sqrt[3]{x+1}");
+    }
+
+    #[test]
+    fn test_parse_large_operators() {
+        let arena = Bump::new();
+        let nodes = parse(&arena, r"\sum_{i=1}^{n} i^2 = N");
+        assert_eq!(nodes.len(), 3);
+        let MathNode::LargeOp {
+            operator,
+            lower_limit,
+            upper_limit,
+            integrand,
+            ..
+        } = &nodes[0]
+        else {
+            panic!("expected a large operator, got {:?}", nodes[0]);
+        };
+        assert_eq!(*operator, LargeOperator::Sum);
+        assert_eq!(lower_limit.as_ref().unwrap().len(), 3);
+        assert_eq!(upper_limit.as_deref(), Some(&[text("n")][..]));
+        assert_eq!(integrand.as_ref().unwrap().len(), 1);
+        assert_eq!(nodes[1], MathNode::Operator(Operator::Equals));
+
+        assert_eq!(
+            round_trip(r"\int_0^\infty e^{-x}\,dx"),
+            r"\int_{0}^{\infty} e^{-x}\,dx"
+        );
+        assert_eq!(
+            round_trip(r"\lim\limits_{x \to 0} f"),
+            r"\lim_{x\rightarrow0} f"
+        );
+    }
+
+    #[test]
+    fn test_parse_accents_styles_and_text() {
+        assert_eq!(round_trip(r"\hat{x} + \vec v"), r"\hat{x}+\vec{v}");
+        assert_eq!(round_trip(r"\mathbb{R}"), r"\mathbb{R}");
+        assert_eq!(round_trip(r"\alpha\Omega"), r"\alpha\Omega");
+
+        let arena = Bump::new();
+        let nodes = parse(&arena, r"\text{if } x");
+        let MathNode::Run {
+            content, literal, ..
+        } = &nodes[0]
+        else {
+            panic!("expected a text run, got {:?}", nodes[0]);
+        };
+        assert_eq!(content, &vec![text("if ")]);
+        assert_eq!(*literal, Some(true));
+    }
+
+    #[test]
+    fn test_parse_functions_and_fences() {
+        assert_eq!(round_trip(r"\sin x"), r"\sin{x}");
+        assert_eq!(round_trip(r"\sin^2 x"), r"\sin{x}^{2}");
+        assert_eq!(
+            round_trip(r"\operatorname{sgn} x"),
+            r"\operatorname{sgn}{x}"
+        );
+        assert_eq!(
+            round_trip(r"\left( \frac{a}{b} \right]"),
+            r"\left(\frac{a}{b}\right]"
+        );
+        assert_eq!(round_trip(r"\left\{ x \right."), r"\left\{x\right.");
+    }
+
+    #[test]
+    fn test_parse_matrices() {
+        let arena = Bump::new();
+        let nodes = parse(&arena, r"\begin{pmatrix} a & b \\ c & d \\ \end{pmatrix}");
+        let MathNode::Matrix {
+            rows, fence_type, ..
+        } = &nodes[0]
+        else {
+            panic!("expected a matrix, got {:?}", nodes[0]);
+        };
+        assert_eq!(*fence_type, MatrixFence::Paren);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[1], vec![vec![text("c")], vec![text("d")]]);
+
+        assert_eq!(
+            round_trip(r"\begin{bmatrix}1&0\\0&1\end{bmatrix}"),
+            r"\begin{bmatrix}1 & 0 \\ 0 & 1\end{bmatrix}"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let arena = Bump::new();
+        let parser = LatexParser::new(&arena);
+
+        let err = parser.parse(r"x + \foo{y}").unwrap_err();
+        assert!(matches!(
+            &err,
+            LatexError::Unsupported { command, offset: 4 } if command == "\\foo"
+        ));
+        assert!(err.to_string().contains("\\foo"));
+
+        let err = parser.parse(r"\begin{align} x \end{align}").unwrap_err();
+        assert!(
+            matches!(err, LatexError::Unsupported { ref command, .. } if command == "\\begin{align}")
+        );
+
+        for (latex, offset) in [
+            (r"\frac{a}{b", 8),
+            ("a}", 1),
+            ("a & b", 2),
+            (r"\left( x", 0),
+            (r"x \right)", 2),
+            (r"\begin{matrix} a \end{pmatrix}", 17),
+            ("x^", 2),
+            ("x^2^3", 3),
+            (r"\sqrt", 5),
+        ] {
+            match parser.parse(latex) {
+                Err(LatexError::Syntax { offset: at, .. }) => {
+                    assert_eq!(at, offset, "wrong offset for {:?}", latex)
+                },
+                other => panic!("expected a syntax error for {:?}, got {:?}", latex, other),
+            }
+        }
+
+        let deep = "{".repeat(1000);
+        assert!(parser.parse(&deep).is_err());
+    }
+}
@@ -35,9 +35,10 @@
 /// The design is inspired by the plurimath Ruby project but adapted for Rust's
 /// type system and performance characteristics.
 pub mod ast;
-/// LaTeX Converter
+/// LaTeX Converter and Parser
 ///
-/// This module converts our formula AST to LaTeX format.
+/// This module converts our formula AST to LaTeX format, and parses
+/// LaTeX math back into the AST.
 /// LaTeX is a widely-used typesetting system for mathematical formulas.
 pub mod latex;
 /// MTEF (MathType Equation Format) Parser
@@ -52,9 +53,10 @@ pub mod latex;
 /// - http://rtf2latex2e.sourceforge.net/MTEF5.html
 /// - rtf2latex2e source code
 mod mtef;
-/// OMML (Office Math Markup Language) Parser
+/// OMML (Office Math Markup Language) Parser and Writer
 ///
-/// This module parses Microsoft Office Math Markup Language (OMML) into our AST.
+/// This module parses Microsoft Office Math Markup Language (OMML) into our AST,
+/// and writes the AST back to OMML.
 /// OMML is used in modern Office documents (.docx, .pptx, etc.) to represent
 /// mathematical formulas.
 ///
@@ -73,9 +75,9 @@ pub use ast::{
     AccentType, Fence, Formula, FormulaBuilder, LargeOperator, MathNode, MatrixFence, Operator,
     SpaceType, StyleType, Symbol,
 };
-pub use latex::{LatexConverter, LatexError};
+pub use latex::{LatexConverter, LatexError, LatexParser};
 pub use mtef::{MtefError, MtefParser};
-pub use omml::{OmmlError, OmmlParser};
+pub use omml::{OmmlError, OmmlParser, OmmlWriter};
 
 /// Conversion error that wraps all possible formula errors
 #[derive(Debug)]
@@ -83,6 +85,8 @@ pub enum FormulaError {
     Omml(OmmlError),
     Latex(LatexError),
     Mtef(MtefError),
+    /// The requested conversion is not supported
+    Unsupported(String),
 }
 
 impl std::fmt::Display for FormulaError {
@@ -91,6 +95,7 @@ impl std::fmt::Display for FormulaError {
             FormulaError::Omml(e) => write!(f, "OMML error: {}", e),
             FormulaError::Latex(e) => write!(f, "LaTeX error: {}", e),
             FormulaError::Mtef(e) => write!(f, "MTEF error: {}", e),
+            FormulaError::Unsupported(msg) => write!(f, "Unsupported conversion: {}", msg),
         }
     }
 }
@@ -154,18 +159,36 @@ pub fn mtef_to_latex(mtef_data: &[u8]) -> Result<String, FormulaError> {
     Ok(converter.convert(&formula)?.to_string())
 }
 
-/// Convert OMML to MTEF (not yet implemented)
+/// Convert OMML to MTEF
 ///
-/// This function is planned for future implementation.
+/// Writing MTEF is not supported, so this always returns
+/// [`FormulaError::Unsupported`].
 pub fn omml_to_mtef(_omml: &str) -> Result<Vec<u8>, FormulaError> {
-    unimplemented!("OMML to MTEF conversion is not yet implemented")
+    Err(FormulaError::Unsupported(
+        "writing MTEF is not supported".to_string(),
+    ))
 }
 
-/// Convert MTEF to OMML (not yet implemented)
+/// Convert MTEF binary data to OMML
 ///
-/// This function is planned for future implementation.
-pub fn mtef_to_omml(_mtef_data: &[u8]) -> Result<String, FormulaError> {
-    unimplemented!("MTEF to OMML conversion is not yet implemented")
+/// The result is an `m:oMath` element without namespace declarations;
+/// see [`OmmlWriter`]. Data that holds no MTEF equation is an error.
+///
+/// # Example
+/// ```ignore
+/// let omml = mtef_to_omml(mtef_data)?;
+/// println!("OMML: {}", omml);
+/// ```
+pub fn mtef_to_omml(mtef_data: &[u8]) -> Result<String, FormulaError> {
+    let formula = Formula::new();
+    let mut parser = MtefParser::new(formula.arena(), mtef_data);
+    if !parser.is_valid() {
+        return Err(MtefError::InvalidFormat("no MTEF equation found".to_string()).into());
+    }
+    let nodes = parser.parse()?;
+
+    let mut writer = OmmlWriter::new();
+    Ok(writer.write_nodes(&nodes)?.to_string())
 }
 
 #[cfg(test)]
@@ -179,6 +202,23 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_mtef_to_omml() {
+        assert!(matches!(
+            mtef_to_omml(&[0u8; 10]),
+            Err(FormulaError::Mtef(_))
+        ));
+    }
+
+    #[test]
+    fn test_omml_to_mtef_unsupported() {
+        let omml = r#"<m:oMath><m:r><m:t>x</m:t></m:r></m:oMath>"#;
+        assert!(matches!(
+            omml_to_mtef(omml),
+            Err(FormulaError::Unsupported(_))
+        ));
+    }
+
     #[test]
     fn test_formula_creation() {
         let formula = Formula::new();
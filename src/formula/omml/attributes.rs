@@ -47,6 +47,7 @@ pub fn parse_fence_type(open: Option<&str>, close: Option<&str>) -> (Option<Fenc
         "⟪" | "&#10218;" => Some(Fence::AngleBracket),
         "⟦" | "&#10214;" => Some(Fence::SquareBracket),
         "⦃" => Some(Fence::CurlyBrace),
+        "" => Some(Fence::None),
         _ => None,
     });
 
@@ -62,6 +63,7 @@ pub fn parse_fence_type(open: Option<&str>, close: Option<&str>) -> (Option<Fenc
         "⟫" | "&#10219;" => Some(Fence::AngleBracket),
         "⟧" | "&#10215;" => Some(Fence::SquareBracket),
         "⦄" => Some(Fence::CurlyBrace),
+        "" => Some(Fence::None),
         _ => None,
     });
 
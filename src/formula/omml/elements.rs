@@ -54,6 +54,8 @@ pub enum ElementType {
     Scr,
     Sty,
     Nor,
+    /// Simple property element such as `m:type` or `m:subHide`
+    PropertyValue,
     Unknown,
 }
 
@@ -227,6 +229,8 @@ pub struct ElementContext<'arena> {
     pub text: TextBuffer,
     pub properties: ElementProperties,
     pub attributes: Vec<quick_xml::events::attributes::Attribute<'static>>,
+    /// `m:val` attribute of property elements such as `m:chr` or `m:type`
+    pub value: Option<String>,
 
     // Core mathematical components
     pub base: Option<Vec<MathNode<'arena>>>,
@@ -273,6 +277,7 @@ impl<'arena> ElementContext<'arena> {
             text: TextBuffer::new(),
             properties: ElementProperties::default(),
             attributes: Vec::new(),
+            value: None,
             base: None,
             numerator: None,
             denominator: None,
@@ -304,6 +309,7 @@ impl<'arena> ElementContext<'arena> {
         self.text.clear();
         self.properties = ElementProperties::default();
         self.attributes.clear();
+        self.value = None;
         self.base = None;
         self.numerator = None;
         self.denominator = None;
@@ -328,6 +334,14 @@ impl<'arena> ElementContext<'arena> {
         self.character_data = None;
     }
 
+    /// Value of a property element: its `m:val` attribute, or its text
+    /// content for the element-text form (`<m:chr>∑</m:chr>`).
+    pub fn property_value(&self) -> Option<&str> {
+        self.value
+            .as_deref()
+            .or_else(|| Some(self.text.as_str().trim()).filter(|text| !text.is_empty()))
+    }
+
     /// Check if the context has any content
     #[inline]
     #[allow(dead_code)]
@@ -13,17 +13,17 @@ impl BarHandler {
         parent_context: Option<&mut ElementContext<'arena>>,
         _arena: &'arena bumpalo::Bump, // Unused: simple wrapper, children are owned Vec
     ) {
-        let base = if context.children.is_empty() {
-            Vec::new()
-        } else {
-            context.children.clone()
-        };
+        let base = context
+            .base
+            .take()
+            .unwrap_or_else(|| context.children.clone());
 
         // Use dedicated position parsing function
         let position = context
             .properties
-            .alignment
+            .accent_position
             .as_ref()
+            .or(context.properties.alignment.as_ref())
             .and_then(|s| parse_position_type(Some(s)));
 
         let node = MathNode::Bar {
@@ -1,6 +1,5 @@
 // Character element handler
 
-use crate::formula::omml::attributes::get_attribute_value_borrowed;
 use crate::formula::omml::elements::ElementContext;
 
 /// Handler for character elements (used within properties)
@@ -14,17 +13,11 @@ impl CharHandler {
         _arena: &'arena bumpalo::Bump, // Unused: character value stored in context properties
     ) {
         if let Some(parent) = parent_context {
-            // Get character value from either val attribute or text content
-            // Use zero-copy borrowed version and convert to owned only when storing
-            let char_value = get_attribute_value_borrowed(&context.attributes, "val")
-                .map(|cow| cow.into_owned())
-                .or_else(|| {
-                    if !context.text.is_empty() {
-                        Some(context.text.as_str().to_string())
-                    } else {
-                        None
-                    }
-                });
+            // An empty value is meaningful here: `begChr=""` means no fence
+            let char_value = context
+                .value
+                .clone()
+                .or_else(|| (!context.text.is_empty()).then(|| context.text.as_str().to_string()));
 
             if let Some(value) = char_value {
                 match std::str::from_utf8(elem).unwrap_or("") {
@@ -34,6 +27,9 @@ impl CharHandler {
                     "endChr" | "m:endChr" => {
                         parent.properties.delimiter_close_char = Some(value);
                     },
+                    "sepChr" | "m:sepChr" => {
+                        parent.properties.delimiter_separator_char = Some(value);
+                    },
                     _ => {
                         parent.properties.chr = Some(value);
                     },
@@ -1,5 +1,6 @@
 // Component element handlers
 
+use crate::formula::ast::MathNode;
 use crate::formula::omml::elements::{ElementContext, ElementType};
 use crate::formula::omml::utils::extend_vec_efficient;
 
@@ -83,6 +84,9 @@ impl BaseHandler {
                 ElementType::Accent | ElementType::Bar | ElementType::GroupChar => {
                     parent.base = Some(context.children.clone());
                 },
+                ElementType::LimLow | ElementType::LimUpp | ElementType::PreScript => {
+                    parent.base = Some(context.children.clone());
+                },
                 ElementType::Nary => {
                     // For n-ary operators, the e element is the integrand
                     parent.integrand = Some(context.children.clone());
@@ -91,6 +95,12 @@ impl BaseHandler {
                     // For equation arrays, each e element is a row
                     parent.eq_array_rows.push(context.children.clone());
                 },
+                ElementType::MatrixRow => {
+                    // Keep each matrix cell together so the row can tell them apart
+                    parent
+                        .children
+                        .push(MathNode::Row(std::mem::take(&mut context.children)));
+                },
                 _ => {
                     // Pass children up for other contexts
                     extend_vec_efficient(&mut parent.children, context.children.clone());
@@ -177,10 +187,14 @@ impl LimUppHandler {
                 parent.upper_limit = Some(context.children.clone());
             } else {
                 // If not in nary context, treat as overset
-                crate::formula::omml::utils::extend_vec_efficient(
-                    &mut parent.children,
-                    context.children.clone(),
-                );
+                parent.children.push(MathNode::Over {
+                    base: context
+                        .base
+                        .take()
+                        .unwrap_or_else(|| context.children.clone()),
+                    over: context.upper_limit.take().unwrap_or_default(),
+                    position: None,
+                });
             }
         }
     }
@@ -200,10 +214,14 @@ impl LimLowHandler {
                 parent.lower_limit = Some(context.children.clone());
             } else {
                 // If not in nary context, treat as underset
-                crate::formula::omml::utils::extend_vec_efficient(
-                    &mut parent.children,
-                    context.children.clone(),
-                );
+                parent.children.push(MathNode::Under {
+                    base: context
+                        .base
+                        .take()
+                        .unwrap_or_else(|| context.children.clone()),
+                    under: context.lower_limit.take().unwrap_or_default(),
+                    position: None,
+                });
             }
         }
     }
@@ -1,6 +1,7 @@
 // Function element handlers
 
 use crate::formula::ast::*;
+use crate::formula::omml::attributes::parse_large_operator;
 use crate::formula::omml::elements::ElementContext;
 use std::borrow::Cow;
 
@@ -20,15 +21,27 @@ impl FunctionHandler {
             context.children.clone()
         };
 
-        let name = context
-            .function_name
-            .take()
-            .unwrap_or_else(|| "f".to_string());
-        let name = arena.alloc_str(&name);
+        // Limit-like names (`lim` over a limLow) become large operators
+        let node = if let Some(operator) = context.operator.take() {
+            MathNode::LargeOp {
+                operator,
+                lower_limit: context.lower_limit.take(),
+                upper_limit: context.upper_limit.take(),
+                integrand: Some(argument),
+                hide_lower: false,
+                hide_upper: false,
+            }
+        } else {
+            let name = context
+                .function_name
+                .take()
+                .unwrap_or_else(|| "f".to_string());
+            let name = arena.alloc_str(&name);
 
-        let node = MathNode::Function {
-            name: Cow::Borrowed(name),
-            argument,
+            MathNode::Function {
+                name: Cow::Borrowed(name),
+                argument,
+            }
         };
 
         if let Some(parent) = parent_context {
@@ -46,18 +59,44 @@ impl FunctionNameHandler {
         parent_context: Option<&mut ElementContext<'arena>>,
         _arena: &'arena bumpalo::Bump, // Unused: function name stored as owned String in parent context
     ) {
-        // Collect text from children to form function name
-        let mut name = String::new();
-        for child in &context.children {
-            if let MathNode::Text(text) = child {
-                name.push_str(text.as_ref());
+        let Some(parent) = parent_context else {
+            return;
+        };
+
+        // A name written under or over its limit, as Word does for `lim`
+        if let [
+            MathNode::Under { base, under, .. }
+            | MathNode::Over {
+                base, over: under, ..
+            },
+        ] = context.children.as_slice()
+            && let Some(operator) = parse_large_operator(Some(&collect_text(base)))
+        {
+            parent.operator = Some(operator);
+            match context.children[0] {
+                MathNode::Under { .. } => parent.lower_limit = Some(under.clone()),
+                _ => parent.upper_limit = Some(under.clone()),
             }
+            return;
         }
 
-        if !name.is_empty()
-            && let Some(parent) = parent_context
-        {
+        // Collect text from children to form function name
+        let name = collect_text(&context.children);
+        if !name.is_empty() {
             parent.function_name = Some(name);
         }
     }
 }
+
+/// Concatenate the text of plain and styled runs.
+fn collect_text(nodes: &[MathNode]) -> String {
+    let mut name = String::new();
+    for node in nodes {
+        match node {
+            MathNode::Text(text) => name.push_str(text.as_ref()),
+            MathNode::Run { content, .. } => name.push_str(&collect_text(content)),
+            _ => {},
+        }
+    }
+    name
+}
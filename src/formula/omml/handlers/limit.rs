@@ -1,6 +1,6 @@
 // Limit element handler
 
-use crate::formula::omml::elements::ElementContext;
+use crate::formula::omml::elements::{ElementContext, ElementType};
 
 /// Handler for limit elements
 pub struct LimitHandler;
@@ -12,11 +12,17 @@ impl LimitHandler {
         _arena: &'arena bumpalo::Bump, // Unused: limit elements are owned Vec from context
     ) {
         if let Some(parent) = parent_context {
-            // Limits are handled by the specific handlers above
-            crate::formula::omml::utils::extend_vec_efficient(
-                &mut parent.children,
-                context.children.clone(),
-            );
+            match parent.element_type {
+                ElementType::LimLow => parent.lower_limit = Some(context.children.clone()),
+                ElementType::LimUpp => parent.upper_limit = Some(context.children.clone()),
+                _ => {
+                    // Limits outside limLow/limUpp just pass their content up
+                    crate::formula::omml::utils::extend_vec_efficient(
+                        &mut parent.children,
+                        context.children.clone(),
+                    );
+                },
+            }
         }
     }
 }
@@ -12,12 +12,11 @@ impl LitHandler {
         _arena: &'arena bumpalo::Bump, // Unused: formatting handler, sets flags in context
     ) {
         if let Some(parent) = parent_context {
-            // Set literal property based on element content
-            let text_content = context.text.as_str().trim();
-            if !text_content.is_empty() {
-                parent.properties.run_literal =
-                    Some(text_content == "1" || text_content.to_lowercase() == "true");
-            }
+            // Set literal property; an empty element means on
+            parent.properties.run_literal =
+                Some(context.property_value().is_none_or(|value| {
+                    matches!(value.to_lowercase().as_str(), "1" | "true" | "on")
+                }));
         }
     }
 }
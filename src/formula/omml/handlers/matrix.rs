@@ -90,12 +90,15 @@ impl MatrixRowHandler {
             && parent.element_type == ElementType::Matrix
         {
             // Matrix row - collect cells from children
-            // Each child represents a cell (mtd element)
-            let mut row = Vec::new();
-            for child in &context.children {
-                // Each child is a cell containing mathematical content
-                row.push(vec![child.clone()]);
-            }
+            // Each m:e cell arrives wrapped in a Row holding its content
+            let row = context
+                .children
+                .drain(..)
+                .map(|child| match child {
+                    MathNode::Row(cell) => cell,
+                    other => vec![other],
+                })
+                .collect();
             parent.matrix_rows.push(row);
         }
     }
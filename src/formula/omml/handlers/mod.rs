@@ -23,6 +23,7 @@ mod phantom;
 mod pos;
 mod post_script;
 mod pre_script;
+mod property_value;
 mod radical;
 mod run_props;
 mod scr;
@@ -56,6 +57,7 @@ pub use phantom::PhantomHandler;
 pub use pos::PosHandler;
 pub use post_script::PostScriptHandler;
 pub use pre_script::PreScriptHandler;
+pub use property_value::PropertyValueHandler;
 pub use radical::RadicalHandler;
 pub use run_props::RunPropsHandler;
 pub use scr::ScrHandler;
@@ -33,13 +33,19 @@ impl NaryHandler {
         parent_context: Option<&mut ElementContext<'arena>>,
         _arena: &'arena bumpalo::Bump, // Unused: limits and integrand are owned Vec from context
     ) {
-        // Get operator from properties (set by chr child element)
+        // Get operator from properties (set by chr child element); OMML
+        // defaults to an integral when no character is given
         let operator = context
             .properties
             .chr
             .as_ref()
             .and_then(|chr| parse_large_operator(Some(chr)))
-            .or(context.operator);
+            .or(context.operator)
+            .or(context
+                .properties
+                .chr
+                .is_none()
+                .then_some(LargeOperator::Integral));
 
         if let Some(operator) = operator {
             let lower_limit = context.lower_limit.take();
@@ -13,10 +13,9 @@ impl NorHandler {
     ) {
         if let Some(parent) = parent_context {
             // Set normal text font based on element content
-            let text_content = context.text.as_str().trim();
-            if !text_content.is_empty() {
-                parent.properties.font = Some(text_content.to_string());
-                parent.properties.run_normal_text = Some(text_content.to_string());
+            if let Some(value) = context.property_value() {
+                parent.properties.font = Some(value.to_string());
+                parent.properties.run_normal_text = Some(value.to_string());
             }
         }
     }
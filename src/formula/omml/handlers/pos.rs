@@ -13,9 +13,8 @@ impl PosHandler {
     ) {
         if let Some(parent) = parent_context {
             // Set position property based on element content
-            let text_content = context.text.as_str().trim();
-            if !text_content.is_empty() {
-                parent.properties.accent_position = Some(text_content.to_string());
+            if let Some(value) = context.property_value() {
+                parent.properties.accent_position = Some(value.to_string());
             }
         }
     }
@@ -1,5 +1,6 @@
 // Pre-script element handler
 
+use crate::formula::ast::MathNode;
 use crate::formula::omml::elements::ElementContext;
 
 /// Handler for pre-script elements
@@ -12,11 +13,33 @@ impl PreScriptHandler {
         _arena: &'arena bumpalo::Bump, // Unused: script positioning, no allocation needed
     ) {
         if let Some(parent) = parent_context {
-            // Pre-scripts are handled by the superscript/subscript elements
-            crate::formula::omml::utils::extend_vec_efficient(
-                &mut parent.pre_scripts,
-                context.children.clone(),
-            );
+            // The m:sub, m:sup and m:e children were stored by their handlers
+            let base = context
+                .base
+                .take()
+                .unwrap_or_else(|| context.children.clone());
+            let pre_subscript = context.subscript.take().filter(|s| !s.is_empty());
+            let pre_superscript = context.superscript.take().filter(|s| !s.is_empty());
+            let node = match (pre_subscript, pre_superscript) {
+                (Some(pre_subscript), Some(pre_superscript)) => MathNode::PreSubSup {
+                    base,
+                    pre_subscript,
+                    pre_superscript,
+                },
+                (Some(pre_subscript), None) => MathNode::PreSub {
+                    base,
+                    pre_subscript,
+                },
+                (None, Some(pre_superscript)) => MathNode::PreSup {
+                    base,
+                    pre_superscript,
+                },
+                (None, None) => {
+                    crate::formula::omml::utils::extend_vec_efficient(&mut parent.children, base);
+                    return;
+                },
+            };
+            parent.children.push(node);
         }
     }
 }
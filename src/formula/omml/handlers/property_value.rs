@@ -0,0 +1,35 @@
+// Simple property element handler
+
+use crate::formula::omml::elements::ElementContext;
+
+/// Handler for simple property elements (m:type, m:subHide, m:supHide, m:degHide)
+pub struct PropertyValueHandler;
+
+impl PropertyValueHandler {
+    pub fn handle_end<'arena>(
+        name: &str,
+        context: &mut ElementContext<'arena>,
+        parent_context: Option<&mut ElementContext<'arena>>,
+    ) {
+        let Some(parent) = parent_context else {
+            return;
+        };
+
+        // On/off properties default to on when the element has no value
+        let flag = || {
+            context
+                .property_value()
+                .is_none_or(|value| matches!(value, "1" | "on" | "true"))
+        };
+
+        match name.strip_prefix("m:").unwrap_or(name) {
+            "type" => {
+                parent.properties.fraction_type = context.property_value().map(str::to_string);
+            },
+            "subHide" => parent.properties.nary_hide_sub = Some(flag()),
+            "supHide" => parent.properties.nary_hide_sup = Some(flag()),
+            "degHide" => parent.properties.radical_hide_degree = Some(flag()),
+            _ => {},
+        }
+    }
+}
@@ -13,7 +13,9 @@ impl RadicalHandler {
         _arena: &'arena bumpalo::Bump, // Unused: base and index are owned Vec from context
     ) {
         let base = context.base.take().unwrap_or_default();
-        let index = context.degree.take();
+        let index = context.degree.take().filter(|degree| {
+            !degree.is_empty() && context.properties.radical_hide_degree != Some(true)
+        });
 
         let node = MathNode::Root { base, index };
 
@@ -13,18 +13,28 @@ impl RunPropsHandler {
     ) {
         // Run properties are stored in the parent context
         if let Some(parent) = parent_context {
-            // Copy all run-related properties from this context to parent
-            parent.properties.run_literal = context.properties.run_literal;
-            parent.properties.run_normal_text = context.properties.run_normal_text.clone();
-            parent.properties.run_math_style = context.properties.run_math_style.clone();
-            parent.properties.math_variant = context.properties.math_variant.clone();
-            parent.properties.display_style = context.properties.display_style;
-            parent.properties.font = context.properties.font.clone();
-            parent.properties.color = context.properties.color.clone();
-            parent.properties.underline = context.properties.underline.clone();
-            parent.properties.overline = context.properties.overline.clone();
-            parent.properties.strike_through = context.properties.strike_through;
-            parent.properties.double_strike_through = context.properties.double_strike_through;
+            // Merge run-related properties into the parent, since a run can
+            // carry both m:rPr and w:rPr and both arrive here
+            let props = &context.properties;
+            let target = &mut parent.properties;
+            target.run_literal = props.run_literal.or(target.run_literal);
+            target.run_normal_text = props
+                .run_normal_text
+                .clone()
+                .or(target.run_normal_text.take());
+            target.run_math_style = props
+                .run_math_style
+                .clone()
+                .or(target.run_math_style.take());
+            target.math_variant = props.math_variant.clone().or(target.math_variant.take());
+            target.display_style = props.display_style.or(target.display_style);
+            target.font = props.font.clone().or(target.font.take());
+            target.color = props.color.clone().or(target.color.take());
+            target.underline = props.underline.clone().or(target.underline.take());
+            target.overline = props.overline.clone().or(target.overline.take());
+            target.strike_through = props.strike_through.or(target.strike_through);
+            target.double_strike_through =
+                props.double_strike_through.or(target.double_strike_through);
         }
     }
 }
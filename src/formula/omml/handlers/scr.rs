@@ -13,10 +13,8 @@ impl ScrHandler {
     ) {
         if let Some(parent) = parent_context {
             // Set math variant based on element content
-            let text_content = context.text.as_str().trim();
-            if !text_content.is_empty() {
-                parent.properties.math_variant = Some(text_content.to_string());
-                parent.properties.run_math_style = Some(text_content.to_string());
+            if let Some(value) = context.property_value() {
+                parent.properties.math_variant = Some(value.to_string());
             }
         }
     }
@@ -80,7 +80,8 @@ impl SuperscriptElementHandler {
         if let Some(parent) = parent_context {
             match parent.element_type {
                 crate::formula::omml::elements::ElementType::Superscript
-                | crate::formula::omml::elements::ElementType::SubSup => {
+                | crate::formula::omml::elements::ElementType::SubSup
+                | crate::formula::omml::elements::ElementType::PreScript => {
                     parent.superscript = Some(context.children.clone());
                 },
                 crate::formula::omml::elements::ElementType::Nary => {
@@ -110,7 +111,8 @@ impl SubscriptElementHandler {
         if let Some(parent) = parent_context {
             match parent.element_type {
                 crate::formula::omml::elements::ElementType::Subscript
-                | crate::formula::omml::elements::ElementType::SubSup => {
+                | crate::formula::omml::elements::ElementType::SubSup
+                | crate::formula::omml::elements::ElementType::PreScript => {
                     parent.subscript = Some(context.children.clone());
                 },
                 crate::formula::omml::elements::ElementType::Nary => {
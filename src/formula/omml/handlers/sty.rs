@@ -13,11 +13,10 @@ impl StyHandler {
     ) {
         if let Some(parent) = parent_context {
             // Set display style based on element content
-            let text_content = context.text.as_str().trim();
-            if !text_content.is_empty() {
+            if let Some(value) = context.property_value() {
                 parent.properties.display_style =
-                    Some(matches!(text_content, "d" | "display" | "1" | "true"));
-                parent.properties.run_math_style = Some(text_content.to_string());
+                    Some(matches!(value, "d" | "display" | "1" | "true"));
+                parent.properties.run_math_style = Some(value.to_string());
             }
         }
     }
@@ -13,9 +13,8 @@ impl VertJcHandler {
     ) {
         if let Some(parent) = parent_context {
             // Set vertical alignment property based on element content
-            let text_content = context.text.as_str().trim();
-            if !text_content.is_empty() {
-                parent.properties.vertical_alignment = Some(text_content.to_string());
+            if let Some(value) = context.property_value() {
+                parent.properties.vertical_alignment = Some(value.to_string());
             }
         }
     }
@@ -129,6 +129,16 @@ pub static ELEMENT_TYPES: phf::Map<&'static str, ElementType> = phf_map! {
     "m:borderBoxPr" => ElementType::Properties,
     "mPr" => ElementType::Properties,
     "m:mPr" => ElementType::Properties,
+    "barPr" => ElementType::Properties,
+    "m:barPr" => ElementType::Properties,
+    "sSubSupPr" => ElementType::Properties,
+    "m:sSubSupPr" => ElementType::Properties,
+    "sPrePr" => ElementType::Properties,
+    "m:sPrePr" => ElementType::Properties,
+    "limLowPr" => ElementType::Properties,
+    "m:limLowPr" => ElementType::Properties,
+    "limUppPr" => ElementType::Properties,
+    "m:limUppPr" => ElementType::Properties,
 
     // Characters and content
     "chr" => ElementType::Character,
@@ -141,6 +151,16 @@ pub static ELEMENT_TYPES: phf::Map<&'static str, ElementType> = phf_map! {
     "m:begChr" => ElementType::Character,
     "endChr" => ElementType::Character,
     "m:endChr" => ElementType::Character,
+    "sepChr" => ElementType::Character,
+    "m:sepChr" => ElementType::Character,
+    "type" => ElementType::PropertyValue,
+    "m:type" => ElementType::PropertyValue,
+    "subHide" => ElementType::PropertyValue,
+    "m:subHide" => ElementType::PropertyValue,
+    "supHide" => ElementType::PropertyValue,
+    "m:supHide" => ElementType::PropertyValue,
+    "degHide" => ElementType::PropertyValue,
+    "m:degHide" => ElementType::PropertyValue,
     "e" => ElementType::Base,
     "m:e" => ElementType::Base,
 
@@ -33,12 +33,17 @@ mod properties;
 /// This module provides utility functions for OMML parsing, including
 /// performance optimizations, string processing, and helper functions.
 mod utils;
+/// OMML Writer Implementation
+///
+/// This module serializes our formula AST back to OMML XML.
+mod writer;
 
 use crate::formula::ast::MathNode;
 
 pub use error::OmmlError;
 /// Re-export public API
 pub use parser::OmmlParser;
+pub use writer::OmmlWriter;
 
 #[cfg(test)]
 mod tests {
@@ -1131,4 +1136,72 @@ mod tests {
             _ => panic!("Expected run node"),
         }
     }
+
+    #[test]
+    fn test_parse_val_attributes() {
+        let formula = Formula::new();
+        let parser = OmmlParser::new(formula.arena());
+
+        // Word stores property values in m:val attributes of empty elements
+        let xml = r#"<m:oMath>
+            <m:nary><m:naryPr><m:chr m:val="&#x2211;"/><m:supHide m:val="1"/></m:naryPr>
+                <m:sub><m:r><m:t>i</m:t></m:r></m:sub><m:sup/><m:e><m:r><m:t>i</m:t></m:r></m:e></m:nary>
+            <m:d><m:dPr><m:begChr m:val="["/><m:endChr m:val=""/></m:dPr>
+                <m:e><m:r><m:t>x</m:t></m:r></m:e></m:d>
+            <m:r><m:rPr><m:scr m:val="script"/><m:sty m:val="b"/></m:rPr><m:t>L</m:t></m:r>
+        </m:oMath>"#;
+        let nodes = parser.parse(xml).unwrap();
+
+        assert_eq!(nodes.len(), 3);
+        assert!(matches!(
+            &nodes[0],
+            MathNode::LargeOp {
+                operator: LargeOperator::Sum,
+                hide_upper: true,
+                hide_lower: false,
+                ..
+            }
+        ));
+        assert!(matches!(
+            &nodes[1],
+            MathNode::Fenced {
+                open: Fence::Bracket,
+                close: Fence::None,
+                ..
+            }
+        ));
+        assert!(matches!(
+            &nodes[2],
+            MathNode::Run {
+                style: Some(crate::formula::StyleType::BoldScript),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_parse_matrix_cells_and_pre_scripts() {
+        let formula = Formula::new();
+        let parser = OmmlParser::new(formula.arena());
+
+        let xml = r#"<m:oMath>
+            <m:m><m:mr>
+                <m:e><m:r><m:t>a</m:t></m:r><m:r><m:t>b</m:t></m:r></m:e>
+                <m:e><m:r><m:t>c</m:t></m:r></m:e>
+            </m:mr></m:m>
+            <m:sPre><m:sub><m:r><m:t>1</m:t></m:r></m:sub><m:sup/>
+                <m:e><m:r><m:t>X</m:t></m:r></m:e></m:sPre>
+        </m:oMath>"#;
+        let nodes = parser.parse(xml).unwrap();
+
+        match &nodes[0] {
+            MathNode::Matrix { rows, .. } => {
+                assert_eq!(rows.len(), 1);
+                assert_eq!(rows[0].len(), 2);
+                assert_eq!(rows[0][0].len(), 2);
+            },
+            other => panic!("Expected matrix, got {:?}", other),
+        }
+        assert!(matches!(&nodes[1], MathNode::PreSub { .. }));
+    }
 }
@@ -1,4 +1,4 @@
-use crate::formula::ast::{MathNode, StrikeStyle};
+use crate::formula::ast::{MathNode, StrikeStyle, StyleType};
 use crate::formula::omml::attributes::*;
 use crate::formula::omml::elements::*;
 use crate::formula::omml::error::OmmlError;
@@ -7,7 +7,7 @@ use crate::formula::omml::lookup::*;
 use crate::formula::omml::properties::*;
 use crate::formula::omml::utils::{validate_element_nesting, validate_omml_structure, *};
 use quick_xml::Reader;
-use quick_xml::events::{BytesStart, Event};
+use quick_xml::events::{BytesRef, BytesStart, Event};
 use std::borrow::Cow;
 
 /// OMML parser that converts OMML XML to our formula AST
@@ -73,6 +73,9 @@ impl<'arena> OmmlParser<'arena> {
                 Ok(Event::CData(ref e)) => {
                     self.handle_cdata_element(e, &mut stack)?;
                 },
+                Ok(Event::GeneralRef(ref e)) => {
+                    self.handle_general_ref(e, &mut stack)?;
+                },
                 Ok(Event::Empty(ref e)) => {
                     // Handle self-closing tags
                     self.handle_empty_element(e, &mut stack, &mut result, &mut context_pool)?;
@@ -147,6 +150,9 @@ impl<'arena> OmmlParser<'arena> {
 
         // Create new context for this element using the context pool
         let mut context = context_pool.get(element_type);
+        if takes_value(element_type) {
+            context.value = val_attribute(elem);
+        }
 
         // Parse attributes using SIMD-accelerated parsing with caching
         let attrs: Vec<_> = elem.attributes().filter_map(|a| a.ok()).collect();
@@ -261,6 +267,7 @@ impl<'arena> OmmlParser<'arena> {
                 // Check if run has any properties - if so, create a Run node
                 let has_properties = context.properties.run_literal.is_some()
                     || context.properties.math_variant.is_some()
+                    || context.properties.run_math_style.is_some()
                     || context.properties.run_normal_text.is_some()
                     || context.properties.color.is_some()
                     || context.properties.underline.is_some()
@@ -274,11 +281,10 @@ impl<'arena> OmmlParser<'arena> {
                     let run_node = MathNode::Run {
                         content: std::mem::take(&mut context.children),
                         literal: context.properties.run_literal,
-                        style: context
-                            .properties
-                            .math_variant
-                            .as_ref()
-                            .and_then(|s| parse_style_value(s)),
+                        style: run_style(
+                            context.properties.math_variant.as_deref(),
+                            context.properties.run_math_style.as_deref(),
+                        ),
                         font: context
                             .properties
                             .run_normal_text
@@ -457,6 +463,9 @@ impl<'arena> OmmlParser<'arena> {
             ElementType::Nor => {
                 NorHandler::handle_end(&mut context, parent_context, self.arena);
             },
+            ElementType::PropertyValue => {
+                PropertyValueHandler::handle_end(name_str, &mut context, parent_context);
+            },
             // Handle property elements - store properties in parent and pass children up
             ElementType::Properties => {
                 if let Some(parent) = parent_context {
@@ -525,6 +534,31 @@ impl<'arena> OmmlParser<'arena> {
         Ok(())
     }
 
+    fn handle_general_ref(
+        &self,
+        event: &BytesRef,
+        stack: &mut ElementStack<'arena>,
+    ) -> Result<(), OmmlError> {
+        if let Some(context) = stack.last_mut() {
+            // Entity references are reported apart from the surrounding text
+            if let Some(ch) = event
+                .resolve_char_ref()
+                .map_err(|e| OmmlError::ParseError(e.to_string()))?
+            {
+                context.text.push_str(ch.encode_utf8(&mut [0; 4]));
+            } else {
+                let name = event
+                    .decode()
+                    .map_err(|e| OmmlError::ParseError(e.to_string()))?;
+                if let Some(resolved) = quick_xml::escape::resolve_predefined_entity(&name) {
+                    context.text.push_str(resolved);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     fn handle_empty_element(
         &self,
         elem: &BytesStart,
@@ -539,6 +573,9 @@ impl<'arena> OmmlParser<'arena> {
 
         // For self-closing elements, we need to handle both start and end logic
         let mut context = context_pool.get(element_type);
+        if takes_value(element_type) {
+            context.value = val_attribute(elem);
+        }
 
         // Parse attributes
         let attrs: Vec<_> = elem.attributes().filter_map(|a| a.ok()).collect();
@@ -674,6 +711,27 @@ impl<'arena> OmmlParser<'arena> {
             ElementType::Character => {
                 CharHandler::handle_end(name.as_ref(), &mut context, parent_context, self.arena);
             },
+            ElementType::Position => {
+                PosHandler::handle_end(&mut context, parent_context, self.arena);
+            },
+            ElementType::VerticalAlignment => {
+                VertJcHandler::handle_end(&mut context, parent_context, self.arena);
+            },
+            ElementType::Lit => {
+                LitHandler::handle_end(&mut context, parent_context, self.arena);
+            },
+            ElementType::Scr => {
+                ScrHandler::handle_end(&mut context, parent_context, self.arena);
+            },
+            ElementType::Sty => {
+                StyHandler::handle_end(&mut context, parent_context, self.arena);
+            },
+            ElementType::Nor => {
+                NorHandler::handle_end(&mut context, parent_context, self.arena);
+            },
+            ElementType::PropertyValue => {
+                PropertyValueHandler::handle_end(name_str, &mut context, parent_context);
+            },
             _ => {
                 // For unknown or unhandled self-closing elements, do nothing
             },
@@ -685,3 +743,47 @@ impl<'arena> OmmlParser<'arena> {
         Ok(())
     }
 }
+
+/// Whether elements of this type carry their setting in an `m:val` attribute.
+fn takes_value(element_type: ElementType) -> bool {
+    matches!(
+        element_type,
+        ElementType::Character
+            | ElementType::Position
+            | ElementType::VerticalAlignment
+            | ElementType::Lit
+            | ElementType::Scr
+            | ElementType::Sty
+            | ElementType::Nor
+            | ElementType::PropertyValue
+    )
+}
+
+/// Read the unescaped `m:val` attribute of an element.
+fn val_attribute(elem: &BytesStart) -> Option<String> {
+    elem.attributes()
+        .filter_map(|a| a.ok())
+        .find(|a| a.key.local_name().as_ref() == b"val")
+        .and_then(|a| {
+            a.decode_and_unescape_value(elem.decoder())
+                .ok()
+                .map(|value| value.into_owned())
+        })
+}
+
+/// Combine the script (`m:scr`) and style (`m:sty`) of a run into one style.
+fn run_style(script: Option<&str>, style: Option<&str>) -> Option<StyleType> {
+    let script = script.and_then(parse_style_value);
+    let style = style.and_then(parse_style_value);
+    match (script, style) {
+        (None | Some(StyleType::Normal), style) => style.or(script),
+        (Some(StyleType::SansSerif), Some(StyleType::Bold)) => Some(StyleType::SansSerifBold),
+        (Some(StyleType::SansSerif), Some(StyleType::Italic)) => Some(StyleType::SansSerifItalic),
+        (Some(StyleType::SansSerif), Some(StyleType::BoldItalic)) => {
+            Some(StyleType::SansSerifBoldItalic)
+        },
+        (Some(StyleType::Script), Some(StyleType::Bold)) => Some(StyleType::BoldScript),
+        (Some(StyleType::Fraktur), Some(StyleType::Bold)) => Some(StyleType::BoldFraktur),
+        (script, _) => script,
+    }
+}
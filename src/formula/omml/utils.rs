@@ -552,7 +552,8 @@ pub fn validate_element_nesting(
                 ElementType::Superscript
                 | ElementType::SubSup
                 | ElementType::Nary
-                | ElementType::Integrand,
+                | ElementType::Integrand
+                | ElementType::PreScript,
             ) => {},
             _ => {
                 return Err(super::OmmlError::InvalidStructure(
@@ -565,7 +566,8 @@ pub fn validate_element_nesting(
                 ElementType::Subscript
                 | ElementType::SubSup
                 | ElementType::Nary
-                | ElementType::Integrand,
+                | ElementType::Integrand
+                | ElementType::PreScript,
             ) => {},
             _ => {
                 return Err(super::OmmlError::InvalidStructure(
@@ -0,0 +1,1147 @@
+use crate::common::xml::escape_xml;
+use crate::formula::ast::*;
+use crate::formula::omml::error::OmmlError;
+
+/// Maximum nesting depth of the written tree, guarding against stack overflow
+const MAX_DEPTH: usize = 256;
+
+/// Run properties inherited by the runs inside `Style` and `Run` nodes
+#[derive(Debug, Clone, Copy, Default)]
+struct RunProps {
+    style: Option<StyleType>,
+    literal: bool,
+}
+
+/// OMML writer that serializes our formula AST back to OMML XML
+///
+/// The output is a single `m:oMath` element. It carries no namespace
+/// declarations, so callers embedding it in a document are expected to
+/// declare the `m` prefix (and `w` for line breaks) on an ancestor.
+///
+/// # Example
+/// ```ignore
+/// let mut formula = Formula::new();
+/// formula.set_root(nodes);
+/// let mut writer = OmmlWriter::new();
+/// let omml = writer.write(&formula)?;
+/// ```
+pub struct OmmlWriter {
+    /// Buffer for building the OMML output
+    buffer: String,
+    /// Text of consecutive leaf nodes waiting to be written as one run
+    pending: String,
+    /// Run properties of the pending text
+    pending_props: RunProps,
+    /// Current nesting depth
+    depth: usize,
+}
+
+impl OmmlWriter {
+    /// Create a new OMML writer
+    pub fn new() -> Self {
+        Self {
+            buffer: String::with_capacity(1024),
+            pending: String::new(),
+            pending_props: RunProps::default(),
+            depth: 0,
+        }
+    }
+
+    /// Write a formula as an `m:oMath` element
+    ///
+    /// Returns a reference to avoid unnecessary string cloning.
+    pub fn write(&mut self, formula: &Formula) -> Result<&str, OmmlError> {
+        self.write_nodes(formula.root())
+    }
+
+    /// Write a list of nodes as an `m:oMath` element
+    pub fn write_nodes(&mut self, nodes: &[MathNode]) -> Result<&str, OmmlError> {
+        self.buffer.clear();
+        self.pending.clear();
+        self.depth = 0;
+
+        self.buffer.push_str("<m:oMath>");
+        self.write_content(nodes, RunProps::default())?;
+        self.buffer.push_str("</m:oMath>");
+
+        Ok(&self.buffer)
+    }
+
+    /// Write a node list inside an argument element such as `m:e` or `m:num`
+    fn write_element(
+        &mut self,
+        tag: &str,
+        nodes: &[MathNode],
+        props: RunProps,
+    ) -> Result<(), OmmlError> {
+        // Always write a start/end pair: parsers drop empty `<m:e/>` cells
+        self.open(tag);
+        self.write_content(nodes, props)?;
+        self.close(tag);
+        Ok(())
+    }
+
+    fn write_content(&mut self, nodes: &[MathNode], props: RunProps) -> Result<(), OmmlError> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(OmmlError::DepthLimitExceeded(MAX_DEPTH));
+        }
+
+        for node in nodes {
+            self.write_node(node, props)?;
+        }
+        self.flush_run();
+
+        self.depth -= 1;
+        Ok(())
+    }
+
+    fn write_node(&mut self, node: &MathNode, props: RunProps) -> Result<(), OmmlError> {
+        match node {
+            MathNode::Text(text) | MathNode::Number(text) | MathNode::Error(text) => {
+                self.push_text(text, props);
+            },
+            MathNode::Operator(op) => self.push_text(operator_char(*op), props),
+            MathNode::Symbol(symbol) => match symbol.unicode {
+                Some(ch) => self.push_text(ch.encode_utf8(&mut [0; 4]), props),
+                None => self.push_text(&symbol.name, props),
+            },
+            MathNode::PredefinedSymbol(symbol) => self.push_text(symbol_char(*symbol), props),
+            MathNode::Space(space) => self.push_text(space_text(*space), props),
+            MathNode::LineBreak => {
+                self.flush_run();
+                self.buffer.push_str("<m:r><w:br/></m:r>");
+            },
+            MathNode::Style { style, content } => {
+                let props = RunProps {
+                    style: Some(*style),
+                    ..props
+                };
+                self.write_content(content, props)?;
+            },
+            MathNode::Run {
+                content,
+                literal,
+                style,
+                ..
+            } => {
+                let props = RunProps {
+                    style: style.or(props.style),
+                    literal: literal.unwrap_or(props.literal),
+                };
+                self.write_content(content, props)?;
+            },
+            MathNode::Row(content) => self.write_content(content, props)?,
+            MathNode::Degree(content)
+            | MathNode::Base(content)
+            | MathNode::Argument(content)
+            | MathNode::Numerator(content)
+            | MathNode::Denominator(content)
+            | MathNode::Integrand(content)
+            | MathNode::LowerLimit(content)
+            | MathNode::UpperLimit(content) => self.write_content(content, props)?,
+            MathNode::Limit { content, .. } => self.write_content(content, props)?,
+            _ => {
+                self.flush_run();
+                self.write_structure(node, props)?;
+            },
+        }
+        Ok(())
+    }
+
+    /// Write a node that maps to an OMML object such as `m:f` or `m:nary`
+    fn write_structure(&mut self, node: &MathNode, props: RunProps) -> Result<(), OmmlError> {
+        match node {
+            MathNode::Frac {
+                numerator,
+                denominator,
+                line_thickness,
+                frac_type,
+            } => {
+                let frac_type = match frac_type {
+                    Some(FractionType::NoBar) => Some("noBar"),
+                    Some(FractionType::Skewed) => Some("skw"),
+                    _ if *line_thickness == Some(0.0) => Some("noBar"),
+                    _ => None,
+                };
+                self.open("m:f");
+                if let Some(frac_type) = frac_type {
+                    self.open("m:fPr");
+                    self.property("m:type", frac_type);
+                    self.close("m:fPr");
+                }
+                self.write_element("m:num", numerator, props)?;
+                self.write_element("m:den", denominator, props)?;
+                self.close("m:f");
+            },
+            MathNode::Root { base, index } => {
+                self.open("m:rad");
+                match index {
+                    Some(index) if !index.is_empty() => {
+                        self.write_element("m:deg", index, props)?;
+                    },
+                    _ => {
+                        self.open("m:radPr");
+                        self.property("m:degHide", "1");
+                        self.close("m:radPr");
+                        self.write_element("m:deg", &[], props)?;
+                    },
+                }
+                self.write_element("m:e", base, props)?;
+                self.close("m:rad");
+            },
+            MathNode::Power { base, exponent } => {
+                self.open("m:sSup");
+                self.write_element("m:e", base, props)?;
+                self.write_element("m:sup", exponent, props)?;
+                self.close("m:sSup");
+            },
+            MathNode::Sub { base, subscript } => {
+                self.open("m:sSub");
+                self.write_element("m:e", base, props)?;
+                self.write_element("m:sub", subscript, props)?;
+                self.close("m:sSub");
+            },
+            MathNode::SubSup {
+                base,
+                subscript,
+                superscript,
+            } => {
+                self.open("m:sSubSup");
+                self.write_element("m:e", base, props)?;
+                self.write_element("m:sub", subscript, props)?;
+                self.write_element("m:sup", superscript, props)?;
+                self.close("m:sSubSup");
+            },
+            MathNode::PreSub {
+                base,
+                pre_subscript,
+            } => self.write_pre_script(base, pre_subscript, &[], props)?,
+            MathNode::PreSup {
+                base,
+                pre_superscript,
+            } => self.write_pre_script(base, &[], pre_superscript, props)?,
+            MathNode::PreSubSup {
+                base,
+                pre_subscript,
+                pre_superscript,
+            } => self.write_pre_script(base, pre_subscript, pre_superscript, props)?,
+            MathNode::Under { base, under, .. } => {
+                self.open("m:limLow");
+                self.write_element("m:e", base, props)?;
+                self.write_element("m:lim", under, props)?;
+                self.close("m:limLow");
+            },
+            MathNode::Over { base, over, .. } => {
+                self.open("m:limUpp");
+                self.write_element("m:e", base, props)?;
+                self.write_element("m:lim", over, props)?;
+                self.close("m:limUpp");
+            },
+            MathNode::UnderOver {
+                base, under, over, ..
+            } => {
+                // OMML has no combined form; nest the lower limit inside the upper
+                self.open("m:limUpp");
+                self.open("m:e");
+                self.open("m:limLow");
+                self.write_element("m:e", base, props)?;
+                self.write_element("m:lim", under, props)?;
+                self.close("m:limLow");
+                self.close("m:e");
+                self.write_element("m:lim", over, props)?;
+                self.close("m:limUpp");
+            },
+            MathNode::Fenced {
+                open,
+                content,
+                close,
+                separator,
+            } => {
+                self.write_delimiter(*open, *close, separator.as_deref(), |writer| {
+                    writer.write_element("m:e", content, props)
+                })?;
+            },
+            MathNode::LargeOp {
+                operator,
+                lower_limit,
+                upper_limit,
+                integrand,
+                hide_lower,
+                hide_upper,
+            } => match nary_char(*operator) {
+                Some(chr) => {
+                    let hide_lower = *hide_lower || lower_limit.is_none();
+                    let hide_upper = *hide_upper || upper_limit.is_none();
+                    self.open("m:nary");
+                    self.open("m:naryPr");
+                    self.property("m:chr", chr);
+                    if hide_lower {
+                        self.property("m:subHide", "1");
+                    }
+                    if hide_upper {
+                        self.property("m:supHide", "1");
+                    }
+                    self.close("m:naryPr");
+                    // Absent limits are written as empty elements, as Word does
+                    match lower_limit {
+                        Some(lower_limit) => self.write_element("m:sub", lower_limit, props)?,
+                        None => self.buffer.push_str("<m:sub/>"),
+                    }
+                    match upper_limit {
+                        Some(upper_limit) => self.write_element("m:sup", upper_limit, props)?,
+                        None => self.buffer.push_str("<m:sup/>"),
+                    }
+                    self.write_element("m:e", integrand.as_deref().unwrap_or(&[]), props)?;
+                    self.close("m:nary");
+                },
+                None => {
+                    // Limit-like operators are functions named by a limLow, as Word writes them
+                    self.open("m:func");
+                    self.open("m:fName");
+                    match lower_limit {
+                        Some(lower_limit) => {
+                            self.open("m:limLow");
+                            self.open("m:e");
+                            self.write_function_name(limit_name(*operator));
+                            self.close("m:e");
+                            self.write_element("m:lim", lower_limit, props)?;
+                            self.close("m:limLow");
+                        },
+                        None => self.write_function_name(limit_name(*operator)),
+                    }
+                    self.close("m:fName");
+                    self.write_element("m:e", integrand.as_deref().unwrap_or(&[]), props)?;
+                    self.close("m:func");
+                },
+            },
+            MathNode::Function { name, argument } => {
+                self.write_function(name, argument, props)?;
+            },
+            MathNode::PredefinedFunction { function, argument } => {
+                self.write_function(function_name(*function), argument, props)?;
+            },
+            MathNode::Matrix {
+                rows, fence_type, ..
+            } => {
+                let (open, close) = match fence_type {
+                    MatrixFence::None => (None, None),
+                    MatrixFence::Paren => (Some(Fence::Paren), Some(Fence::Paren)),
+                    MatrixFence::Bracket => (Some(Fence::Bracket), Some(Fence::Bracket)),
+                    MatrixFence::Brace => (Some(Fence::Brace), Some(Fence::Brace)),
+                    MatrixFence::Pipe => (Some(Fence::Pipe), Some(Fence::Pipe)),
+                    MatrixFence::DoublePipe => (Some(Fence::DoublePipe), Some(Fence::DoublePipe)),
+                };
+                match (open, close) {
+                    (Some(open), Some(close)) => {
+                        self.write_delimiter(open, close, None, |writer| {
+                            writer.open("m:e");
+                            writer.write_matrix(rows, props)?;
+                            writer.close("m:e");
+                            Ok(())
+                        })?;
+                    },
+                    _ => self.write_matrix(rows, props)?,
+                }
+            },
+            MathNode::EqArray { rows, .. } => {
+                self.open("m:eqArr");
+                for row in rows {
+                    self.write_element("m:e", row, props)?;
+                }
+                self.close("m:eqArr");
+            },
+            MathNode::Accent { base, accent, .. } => {
+                self.open("m:acc");
+                self.open("m:accPr");
+                self.property("m:chr", accent_char(*accent));
+                self.close("m:accPr");
+                self.write_element("m:e", base, props)?;
+                self.close("m:acc");
+            },
+            MathNode::Bar { base, position } => {
+                self.open("m:bar");
+                if let Some(pos) = position.and_then(position_value) {
+                    self.open("m:barPr");
+                    self.property("m:pos", pos);
+                    self.close("m:barPr");
+                }
+                self.write_element("m:e", base, props)?;
+                self.close("m:bar");
+            },
+            MathNode::BorderBox { content, .. } => {
+                self.open("m:borderBox");
+                self.write_element("m:e", content, props)?;
+                self.close("m:borderBox");
+            },
+            MathNode::GroupChar {
+                base,
+                character,
+                position,
+                vertical_alignment,
+            } => {
+                let pos = position.and_then(position_value);
+                let vert_jc = vertical_alignment.and_then(|alignment| match alignment {
+                    VerticalAlignment::Top => Some("top"),
+                    VerticalAlignment::Bottom => Some("bot"),
+                    _ => None,
+                });
+                self.open("m:groupChr");
+                if character.is_some() || pos.is_some() || vert_jc.is_some() {
+                    self.open("m:groupChrPr");
+                    if let Some(character) = character {
+                        self.property("m:chr", character);
+                    }
+                    if let Some(pos) = pos {
+                        self.property("m:pos", pos);
+                    }
+                    if let Some(vert_jc) = vert_jc {
+                        self.property("m:vertJc", vert_jc);
+                    }
+                    self.close("m:groupChrPr");
+                }
+                self.write_element("m:e", base, props)?;
+                self.close("m:groupChr");
+            },
+            MathNode::Phantom(content) => {
+                self.open("m:phant");
+                self.write_element("m:e", content, props)?;
+                self.close("m:phant");
+            },
+            // Leaves and wrappers are written by write_node
+            _ => {},
+        }
+        Ok(())
+    }
+
+    fn write_pre_script(
+        &mut self,
+        base: &[MathNode],
+        pre_subscript: &[MathNode],
+        pre_superscript: &[MathNode],
+        props: RunProps,
+    ) -> Result<(), OmmlError> {
+        self.open("m:sPre");
+        self.write_element("m:sub", pre_subscript, props)?;
+        self.write_element("m:sup", pre_superscript, props)?;
+        self.write_element("m:e", base, props)?;
+        self.close("m:sPre");
+        Ok(())
+    }
+
+    fn write_delimiter(
+        &mut self,
+        open: Fence,
+        close: Fence,
+        separator: Option<&str>,
+        content: impl FnOnce(&mut Self) -> Result<(), OmmlError>,
+    ) -> Result<(), OmmlError> {
+        // Parentheses are the OMML default and need no properties
+        let beg_chr = (open != Fence::Paren).then(|| fence_chars(open).0);
+        let end_chr = (close != Fence::Paren).then(|| fence_chars(close).1);
+
+        self.open("m:d");
+        if beg_chr.is_some() || end_chr.is_some() || separator.is_some() {
+            self.open("m:dPr");
+            if let Some(beg_chr) = beg_chr {
+                self.property("m:begChr", beg_chr);
+            }
+            if let Some(separator) = separator {
+                self.property("m:sepChr", separator);
+            }
+            if let Some(end_chr) = end_chr {
+                self.property("m:endChr", end_chr);
+            }
+            self.close("m:dPr");
+        }
+        content(self)?;
+        self.close("m:d");
+        Ok(())
+    }
+
+    fn write_matrix(
+        &mut self,
+        rows: &[Vec<Vec<MathNode>>],
+        props: RunProps,
+    ) -> Result<(), OmmlError> {
+        self.open("m:m");
+        for row in rows {
+            self.open("m:mr");
+            for cell in row {
+                self.write_element("m:e", cell, props)?;
+            }
+            self.close("m:mr");
+        }
+        self.close("m:m");
+        Ok(())
+    }
+
+    fn write_function(
+        &mut self,
+        name: &str,
+        argument: &[MathNode],
+        props: RunProps,
+    ) -> Result<(), OmmlError> {
+        self.open("m:func");
+        self.open("m:fName");
+        self.write_function_name(name);
+        self.close("m:fName");
+        self.write_element("m:e", argument, props)?;
+        self.close("m:func");
+        Ok(())
+    }
+
+    /// Write a function name as an upright run
+    fn write_function_name(&mut self, name: &str) {
+        let props = RunProps {
+            style: Some(StyleType::Normal),
+            literal: false,
+        };
+        self.push_text(name, props);
+        self.flush_run();
+    }
+
+    /// Queue leaf text, merging it with the pending run when the properties match
+    fn push_text(&mut self, text: &str, props: RunProps) {
+        if !self.pending.is_empty()
+            && (self.pending_props.style != props.style
+                || self.pending_props.literal != props.literal)
+        {
+            self.flush_run();
+        }
+        self.pending.push_str(text);
+        self.pending_props = props;
+    }
+
+    /// Write the pending text as an `m:r` run
+    fn flush_run(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+
+        let props = self.pending_props;
+        self.buffer.push_str("<m:r>");
+        if props.literal || props.style.is_some() {
+            self.buffer.push_str("<m:rPr>");
+            if props.literal {
+                self.buffer.push_str("<m:lit/>");
+            }
+            if let Some(style) = props.style {
+                let (scr, sty) = style_values(style);
+                if let Some(scr) = scr {
+                    self.property("m:scr", scr);
+                }
+                if let Some(sty) = sty {
+                    self.property("m:sty", sty);
+                }
+            }
+            self.buffer.push_str("</m:rPr>");
+        }
+
+        // Leading or trailing spaces would otherwise be dropped by XML readers
+        if self.pending.starts_with(' ') || self.pending.ends_with(' ') {
+            self.buffer.push_str("<m:t xml:space=\"preserve\">");
+        } else {
+            self.buffer.push_str("<m:t>");
+        }
+        self.buffer.push_str(&escape_xml(&self.pending));
+        self.buffer.push_str("</m:t></m:r>");
+        self.pending.clear();
+    }
+
+    fn open(&mut self, tag: &str) {
+        self.buffer.push('<');
+        self.buffer.push_str(tag);
+        self.buffer.push('>');
+    }
+
+    fn close(&mut self, tag: &str) {
+        self.buffer.push_str("</");
+        self.buffer.push_str(tag);
+        self.buffer.push('>');
+    }
+
+    /// Write a property element such as `<m:chr m:val="∑"/>`
+    fn property(&mut self, tag: &str, value: &str) {
+        self.buffer.push('<');
+        self.buffer.push_str(tag);
+        self.buffer.push_str(" m:val=\"");
+        self.buffer.push_str(&escape_xml(value));
+        self.buffer.push_str("\"/>");
+    }
+}
+
+impl Default for OmmlWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The `m:scr` and `m:sty` values of a style
+fn style_values(style: StyleType) -> (Option<&'static str>, Option<&'static str>) {
+    match style {
+        StyleType::Normal => (None, Some("p")),
+        StyleType::Bold => (None, Some("b")),
+        StyleType::Italic => (None, Some("i")),
+        StyleType::BoldItalic => (None, Some("bi")),
+        StyleType::SansSerif => (Some("sans-serif"), Some("p")),
+        StyleType::SansSerifBold => (Some("sans-serif"), Some("b")),
+        StyleType::SansSerifItalic => (Some("sans-serif"), Some("i")),
+        StyleType::SansSerifBoldItalic => (Some("sans-serif"), Some("bi")),
+        StyleType::Monospace => (Some("monospace"), Some("p")),
+        StyleType::Script => (Some("script"), None),
+        StyleType::BoldScript => (Some("script"), Some("b")),
+        StyleType::Fraktur => (Some("fraktur"), None),
+        StyleType::BoldFraktur => (Some("fraktur"), Some("b")),
+        StyleType::DoubleStruck => (Some("double-struck"), None),
+    }
+}
+
+/// Opening and closing characters of a fence
+fn fence_chars(fence: Fence) -> (&'static str, &'static str) {
+    match fence {
+        Fence::Paren => ("(", ")"),
+        Fence::Bracket => ("[", "]"),
+        Fence::Brace => ("{", "}"),
+        Fence::Angle => ("⟨", "⟩"),
+        Fence::Pipe => ("|", "|"),
+        Fence::DoublePipe => ("‖", "‖"),
+        Fence::Floor => ("⌊", "⌋"),
+        Fence::Ceiling => ("⌈", "⌉"),
+        Fence::AngleBracket => ("⟪", "⟫"),
+        Fence::SquareBracket => ("⟦", "⟧"),
+        Fence::CurlyBrace => ("⦃", "⦄"),
+        Fence::None => ("", ""),
+    }
+}
+
+/// The `m:chr` of operators written as `m:nary`
+fn nary_char(operator: LargeOperator) -> Option<&'static str> {
+    match operator {
+        LargeOperator::Sum => Some("∑"),
+        LargeOperator::Product => Some("∏"),
+        LargeOperator::Coproduct => Some("∐"),
+        LargeOperator::Integral => Some("∫"),
+        LargeOperator::DoubleIntegral => Some("∬"),
+        LargeOperator::TripleIntegral => Some("∭"),
+        LargeOperator::ContourIntegral => Some("∮"),
+        LargeOperator::SurfaceIntegral => Some("∯"),
+        LargeOperator::VolumeIntegral => Some("∰"),
+        LargeOperator::Union | LargeOperator::BigUnion => Some("⋃"),
+        LargeOperator::Intersection | LargeOperator::BigIntersection => Some("⋂"),
+        _ => None,
+    }
+}
+
+/// The name of operators written as a function with a limit
+fn limit_name(operator: LargeOperator) -> &'static str {
+    match operator {
+        LargeOperator::Max => "max",
+        LargeOperator::Min => "min",
+        LargeOperator::Supremum => "sup",
+        LargeOperator::Infimum => "inf",
+        LargeOperator::ArgMax => "argmax",
+        LargeOperator::ArgMin => "argmin",
+        _ => "lim",
+    }
+}
+
+fn function_name(function: FunctionName) -> &'static str {
+    match function {
+        FunctionName::Sin => "sin",
+        FunctionName::Cos => "cos",
+        FunctionName::Tan => "tan",
+        FunctionName::Sec => "sec",
+        FunctionName::Csc => "csc",
+        FunctionName::Cot => "cot",
+        FunctionName::ArcSin => "arcsin",
+        FunctionName::ArcCos => "arccos",
+        FunctionName::ArcTan => "arctan",
+        FunctionName::ArcSec => "arcsec",
+        FunctionName::ArcCsc => "arccsc",
+        FunctionName::ArcCot => "arccot",
+        FunctionName::Sinh => "sinh",
+        FunctionName::Cosh => "cosh",
+        FunctionName::Tanh => "tanh",
+        FunctionName::Sech => "sech",
+        FunctionName::Csch => "csch",
+        FunctionName::Coth => "coth",
+        FunctionName::Log => "log",
+        FunctionName::Ln => "ln",
+        FunctionName::Exp => "exp",
+        FunctionName::Sqrt => "sqrt",
+        FunctionName::Min => "min",
+        FunctionName::Max => "max",
+        FunctionName::Sup => "sup",
+        FunctionName::Inf => "inf",
+        FunctionName::Lim => "lim",
+        FunctionName::Det => "det",
+        FunctionName::Trace => "tr",
+        FunctionName::Dim => "dim",
+        FunctionName::Ker => "ker",
+        FunctionName::Im => "Im",
+        FunctionName::Re => "Re",
+        FunctionName::Arg => "arg",
+        FunctionName::Mod => "mod",
+        FunctionName::Gcd => "gcd",
+        FunctionName::Lcm => "lcm",
+    }
+}
+
+/// Combining character Word uses for each accent
+fn accent_char(accent: AccentType) -> &'static str {
+    match accent {
+        AccentType::Hat => "\u{0302}",
+        AccentType::Check => "\u{030C}",
+        AccentType::Tilde => "\u{0303}",
+        AccentType::Acute => "\u{0301}",
+        AccentType::Grave => "\u{0300}",
+        AccentType::Dot => "\u{0307}",
+        AccentType::DoubleDot => "\u{0308}",
+        AccentType::TripleDot => "\u{20DB}",
+        AccentType::Bar => "\u{0305}",
+        AccentType::Breve => "\u{0306}",
+        AccentType::Vec => "\u{20D7}",
+    }
+}
+
+/// The `m:pos` value of a position, for positions OMML can express
+fn position_value(position: Position) -> Option<&'static str> {
+    match position {
+        Position::Top => Some("top"),
+        Position::Bottom => Some("bot"),
+        _ => None,
+    }
+}
+
+fn space_text(space: SpaceType) -> &'static str {
+    match space {
+        SpaceType::Thin => "\u{2009}",
+        SpaceType::Medium => "\u{205F}",
+        SpaceType::Thick => "\u{2004}",
+        SpaceType::Quad => "\u{2003}",
+        SpaceType::QQuad => "\u{2003}\u{2003}",
+        SpaceType::Negative => "",
+    }
+}
+
+fn operator_char(op: Operator) -> &'static str {
+    match op {
+        Operator::Plus => "+",
+        Operator::Minus => "−",
+        Operator::Multiply | Operator::Times | Operator::Cross => "×",
+        Operator::Divide => "÷",
+        Operator::PlusMinus => "±",
+        Operator::MinusPlus => "∓",
+        Operator::Equals => "=",
+        Operator::NotEquals => "≠",
+        Operator::LessThan => "<",
+        Operator::GreaterThan => ">",
+        Operator::LessThanOrEqual => "≤",
+        Operator::GreaterThanOrEqual => "≥",
+        Operator::Dot => "⋅",
+        Operator::Star => "⋆",
+        Operator::Circle => "○",
+        Operator::Circ => "∘",
+        Operator::Bullet => "∙",
+        Operator::Wedge | Operator::And => "∧",
+        Operator::Vee | Operator::Or => "∨",
+        Operator::Cap | Operator::Intersection => "∩",
+        Operator::Cup | Operator::Union => "∪",
+        Operator::In => "∈",
+        Operator::NotIn => "∉",
+        Operator::Subset => "⊂",
+        Operator::Superset => "⊃",
+        Operator::SubsetEq => "⊆",
+        Operator::SupersetEq => "⊇",
+        Operator::EmptySet => "∅",
+        Operator::Approx => "≈",
+        Operator::Cong => "≅",
+        Operator::Equiv => "≡",
+        Operator::Propto => "∝",
+        Operator::Sim => "∼",
+        Operator::Simeq => "≃",
+        Operator::Asymp => "≍",
+        Operator::Parallel => "∥",
+        Operator::Perpendicular => "⊥",
+        Operator::Angle => "∠",
+        Operator::Nabla => "∇",
+        Operator::Partial => "∂",
+        Operator::Differential => "ⅆ",
+        Operator::Infinity => "∞",
+        Operator::Aleph => "ℵ",
+        Operator::Prime => "′",
+        Operator::DoublePrime => "″",
+        Operator::TriplePrime => "‴",
+        Operator::Ellipsis | Operator::Ldots => "…",
+        Operator::CDots => "⋯",
+        Operator::VDots => "⋮",
+        Operator::DDots => "⋱",
+        Operator::LeftArrow => "←",
+        Operator::RightArrow => "→",
+        Operator::UpArrow => "↑",
+        Operator::DownArrow => "↓",
+        Operator::LeftRightArrow => "↔",
+        Operator::UpDownArrow => "↕",
+        Operator::ForAll => "∀",
+        Operator::Exists => "∃",
+        Operator::Not => "¬",
+        Operator::Implies => "⇒",
+        Operator::Iff => "⇔",
+        Operator::Therefore => "∴",
+        Operator::Because => "∵",
+        Operator::Box | Operator::Square => "□",
+        Operator::Diamond => "◇",
+    }
+}
+
+fn symbol_char(symbol: PredefinedSymbol) -> &'static str {
+    match symbol {
+        PredefinedSymbol::Alpha => "α",
+        PredefinedSymbol::Beta => "β",
+        PredefinedSymbol::Gamma | PredefinedSymbol::EulerGamma => "γ",
+        PredefinedSymbol::Delta => "δ",
+        PredefinedSymbol::Epsilon => "ε",
+        PredefinedSymbol::Zeta => "ζ",
+        PredefinedSymbol::Eta => "η",
+        PredefinedSymbol::Theta => "θ",
+        PredefinedSymbol::Iota => "ι",
+        PredefinedSymbol::Kappa => "κ",
+        PredefinedSymbol::Lambda => "λ",
+        PredefinedSymbol::Mu => "μ",
+        PredefinedSymbol::Nu => "ν",
+        PredefinedSymbol::Xi => "ξ",
+        PredefinedSymbol::Omicron => "ο",
+        PredefinedSymbol::Pi => "π",
+        PredefinedSymbol::Rho => "ρ",
+        PredefinedSymbol::Sigma => "σ",
+        PredefinedSymbol::Tau => "τ",
+        PredefinedSymbol::Upsilon => "υ",
+        PredefinedSymbol::Phi => "φ",
+        PredefinedSymbol::Chi => "χ",
+        PredefinedSymbol::Psi => "ψ",
+        PredefinedSymbol::Omega => "ω",
+        PredefinedSymbol::AlphaCap => "Α",
+        PredefinedSymbol::BetaCap => "Β",
+        PredefinedSymbol::GammaCap => "Γ",
+        PredefinedSymbol::DeltaCap => "Δ",
+        PredefinedSymbol::EpsilonCap => "Ε",
+        PredefinedSymbol::ZetaCap => "Ζ",
+        PredefinedSymbol::EtaCap => "Η",
+        PredefinedSymbol::ThetaCap => "Θ",
+        PredefinedSymbol::IotaCap => "Ι",
+        PredefinedSymbol::KappaCap => "Κ",
+        PredefinedSymbol::LambdaCap => "Λ",
+        PredefinedSymbol::MuCap => "Μ",
+        PredefinedSymbol::NuCap => "Ν",
+        PredefinedSymbol::XiCap => "Ξ",
+        PredefinedSymbol::OmicronCap => "Ο",
+        PredefinedSymbol::PiCap => "Π",
+        PredefinedSymbol::RhoCap => "Ρ",
+        PredefinedSymbol::SigmaCap => "Σ",
+        PredefinedSymbol::TauCap => "Τ",
+        PredefinedSymbol::UpsilonCap => "Υ",
+        PredefinedSymbol::PhiCap => "Φ",
+        PredefinedSymbol::ChiCap => "Χ",
+        PredefinedSymbol::PsiCap => "Ψ",
+        PredefinedSymbol::OmegaCap => "Ω",
+        PredefinedSymbol::Aleph => "ℵ",
+        PredefinedSymbol::ExponentialE => "e",
+        PredefinedSymbol::ImaginaryI => "i",
+        PredefinedSymbol::Infinity => "∞",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formula::latex::LatexParser;
+    use crate::formula::omml::OmmlParser;
+    use std::borrow::Cow;
+
+    fn write(nodes: &[MathNode]) -> String {
+        OmmlWriter::new().write_nodes(nodes).unwrap().to_string()
+    }
+
+    /// Merge adjacent text nodes, since the writer joins consecutive runs
+    fn normalize<'a>(nodes: &[MathNode<'a>]) -> Vec<MathNode<'a>> {
+        let mut result: Vec<MathNode<'a>> = Vec::new();
+        for node in nodes {
+            let node = match node {
+                MathNode::Frac {
+                    numerator,
+                    denominator,
+                    line_thickness,
+                    frac_type,
+                } => MathNode::Frac {
+                    numerator: normalize(numerator),
+                    denominator: normalize(denominator),
+                    line_thickness: *line_thickness,
+                    frac_type: *frac_type,
+                },
+                MathNode::Fenced {
+                    open,
+                    content,
+                    close,
+                    separator,
+                } => MathNode::Fenced {
+                    open: *open,
+                    content: normalize(content),
+                    close: *close,
+                    separator: separator.clone(),
+                },
+                MathNode::Matrix {
+                    rows,
+                    fence_type,
+                    properties,
+                } => MathNode::Matrix {
+                    rows: rows
+                        .iter()
+                        .map(|row| row.iter().map(|cell| normalize(cell)).collect())
+                        .collect(),
+                    fence_type: *fence_type,
+                    properties: properties.clone(),
+                },
+                other => other.clone(),
+            };
+            match (result.last_mut(), &node) {
+                (Some(MathNode::Text(last)), MathNode::Text(text)) => {
+                    last.to_mut().push_str(text);
+                },
+                _ => result.push(node),
+            }
+        }
+        result
+    }
+
+    fn assert_round_trip(xml: &str) {
+        let formula = Formula::new();
+        let parser = OmmlParser::new(formula.arena());
+        let first = parser.parse(xml).unwrap();
+        let written = write(&first);
+        let second = parser
+            .parse(&written)
+            .unwrap_or_else(|e| panic!("failed to reparse {written}: {e}"));
+        assert_eq!(
+            normalize(&first),
+            normalize(&second),
+            "written as {written}"
+        );
+    }
+
+    #[test]
+    fn test_write_text_runs() {
+        let nodes = [
+            MathNode::Number(Cow::Borrowed("2")),
+            MathNode::Operator(Operator::Plus),
+            MathNode::Text(Cow::Borrowed("a<b")),
+        ];
+        assert_eq!(
+            write(&nodes),
+            "<m:oMath><m:r><m:t>2+a&lt;b</m:t></m:r></m:oMath>"
+        );
+
+        let nodes = [MathNode::Text(Cow::Borrowed(" x "))];
+        assert_eq!(
+            write(&nodes),
+            "<m:oMath><m:r><m:t xml:space=\"preserve\"> x </m:t></m:r></m:oMath>"
+        );
+    }
+
+    #[test]
+    fn test_write_run_properties() {
+        let nodes = [
+            MathNode::Text(Cow::Borrowed("x")),
+            MathNode::Run {
+                content: vec![MathNode::Text(Cow::Borrowed("R"))],
+                literal: Some(true),
+                style: Some(StyleType::BoldFraktur),
+                font: None,
+                color: None,
+                underline: None,
+                overline: None,
+                strike_through: None,
+                double_strike_through: None,
+            },
+        ];
+        assert_eq!(
+            write(&nodes),
+            "<m:oMath><m:r><m:t>x</m:t></m:r><m:r><m:rPr><m:lit/><m:scr m:val=\"fraktur\"/>\
+             <m:sty m:val=\"b\"/></m:rPr><m:t>R</m:t></m:r></m:oMath>"
+        );
+    }
+
+    #[test]
+    fn test_write_structures() {
+        let x = || vec![MathNode::Text(Cow::Borrowed("x"))];
+
+        let frac = MathNode::Frac {
+            numerator: x(),
+            denominator: vec![MathNode::Number(Cow::Borrowed("2"))],
+            line_thickness: Some(0.0),
+            frac_type: None,
+        };
+        assert_eq!(
+            write(&[frac]),
+            "<m:oMath><m:f><m:fPr><m:type m:val=\"noBar\"/></m:fPr><m:num><m:r><m:t>x</m:t>\
+             </m:r></m:num><m:den><m:r><m:t>2</m:t></m:r></m:den></m:f></m:oMath>"
+        );
+
+        let root = MathNode::Root {
+            base: x(),
+            index: None,
+        };
+        assert_eq!(
+            write(&[root]),
+            "<m:oMath><m:rad><m:radPr><m:degHide m:val=\"1\"/></m:radPr><m:deg></m:deg>\
+             <m:e><m:r><m:t>x</m:t></m:r></m:e></m:rad></m:oMath>"
+        );
+
+        let sum = MathNode::LargeOp {
+            operator: LargeOperator::Sum,
+            lower_limit: Some(x()),
+            upper_limit: None,
+            integrand: Some(x()),
+            hide_lower: false,
+            hide_upper: false,
+        };
+        assert_eq!(
+            write(&[sum]),
+            "<m:oMath><m:nary><m:naryPr><m:chr m:val=\"∑\"/><m:supHide m:val=\"1\"/></m:naryPr>\
+             <m:sub><m:r><m:t>x</m:t></m:r></m:sub><m:sup/><m:e><m:r><m:t>x</m:t></m:r></m:e>\
+             </m:nary></m:oMath>"
+        );
+
+        let fenced = MathNode::Fenced {
+            open: Fence::Bracket,
+            content: x(),
+            close: Fence::Paren,
+            separator: None,
+        };
+        assert_eq!(
+            write(&[fenced]),
+            "<m:oMath><m:d><m:dPr><m:begChr m:val=\"[\"/></m:dPr><m:e><m:r><m:t>x</m:t></m:r>\
+             </m:e></m:d></m:oMath>"
+        );
+
+        let matrix = MathNode::Matrix {
+            rows: vec![vec![x(), vec![]]],
+            fence_type: MatrixFence::Pipe,
+            properties: None,
+        };
+        assert_eq!(
+            write(&[matrix]),
+            "<m:oMath><m:d><m:dPr><m:begChr m:val=\"|\"/><m:endChr m:val=\"|\"/></m:dPr><m:e>\
+             <m:m><m:mr><m:e><m:r><m:t>x</m:t></m:r></m:e><m:e></m:e></m:mr></m:m></m:e></m:d>\
+             </m:oMath>"
+        );
+    }
+
+    #[test]
+    fn test_write_depth_limit() {
+        let mut node = MathNode::Text(Cow::Borrowed("x"));
+        for _ in 0..MAX_DEPTH {
+            node = MathNode::Row(vec![node]);
+        }
+        assert!(matches!(
+            OmmlWriter::new().write_nodes(&[node]),
+            Err(OmmlError::DepthLimitExceeded(_))
+        ));
+    }
+
+    #[test]
+    fn test_round_trip_fraction_and_scripts() {
+        assert_round_trip(
+            r#"<m:oMath><m:f><m:fPr><m:type m:val="skw"/></m:fPr><m:num><m:r><m:t>a</m:t></m:r></m:num><m:den><m:r><m:t>b</m:t></m:r></m:den></m:f></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:sSubSup><m:e><m:r><m:t>x</m:t></m:r></m:e><m:sub><m:r><m:t>i</m:t></m:r></m:sub><m:sup><m:r><m:t>2</m:t></m:r></m:sup></m:sSubSup><m:sSup><m:e><m:r><m:t>e</m:t></m:r></m:e><m:sup><m:r><m:t>x</m:t></m:r></m:sup></m:sSup></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:sPre><m:sub><m:r><m:t>1</m:t></m:r></m:sub><m:sup><m:r><m:t>2</m:t></m:r></m:sup><m:e><m:r><m:t>X</m:t></m:r></m:e></m:sPre></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:rad><m:radPr><m:degHide m:val="1"/></m:radPr><m:deg/><m:e><m:r><m:t>x</m:t></m:r></m:e></m:rad><m:rad><m:deg><m:r><m:t>3</m:t></m:r></m:deg><m:e><m:r><m:t>y</m:t></m:r></m:e></m:rad></m:oMath>"#,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_nary() {
+        assert_round_trip(
+            r#"<m:oMath><m:nary><m:naryPr><m:chr m:val="∑"/><m:limLoc m:val="undOvr"/></m:naryPr><m:sub><m:r><m:t>i=1</m:t></m:r></m:sub><m:sup><m:r><m:t>n</m:t></m:r></m:sup><m:e><m:r><m:t>i</m:t></m:r></m:e></m:nary></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:nary><m:naryPr><m:subHide m:val="1"/><m:supHide m:val="1"/></m:naryPr><m:sub/><m:sup/><m:e><m:r><m:t>f</m:t></m:r></m:e></m:nary></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:func><m:fName><m:limLow><m:e><m:r><m:rPr><m:sty m:val="p"/></m:rPr><m:t>lim</m:t></m:r></m:e><m:lim><m:r><m:t>n→∞</m:t></m:r></m:lim></m:limLow></m:fName><m:e><m:r><m:t>a</m:t></m:r></m:e></m:func></m:oMath>"#,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_delimiters_and_matrices() {
+        assert_round_trip(
+            r#"<m:oMath><m:d><m:dPr><m:begChr m:val="["/><m:sepChr m:val=";"/><m:endChr m:val="}"/></m:dPr><m:e><m:r><m:t>a</m:t></m:r></m:e><m:e><m:r><m:t>b</m:t></m:r></m:e></m:d></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:d><m:dPr><m:begChr m:val=""/><m:endChr m:val="|"/></m:dPr><m:e><m:r><m:t>a</m:t></m:r></m:e></m:d></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:m><m:mr><m:e><m:r><m:t>a+b</m:t></m:r><m:r><m:t>c</m:t></m:r></m:e><m:e><m:r><m:t>d</m:t></m:r></m:e></m:mr><m:mr><m:e><m:r><m:t>e</m:t></m:r></m:e><m:e></m:e></m:mr></m:m></m:oMath>"#,
+        );
+    }
+
+    #[test]
+    fn test_round_trip_runs_and_decorations() {
+        assert_round_trip(
+            r#"<m:oMath><m:r><m:rPr><m:lit/><m:scr m:val="double-struck"/></m:rPr><m:t>R</m:t></m:r><m:r><m:rPr><m:scr m:val="sans-serif"/><m:sty m:val="bi"/></m:rPr><m:t>v</m:t></m:r><m:r><m:rPr><m:sty m:val="p"/></m:rPr><m:t>d</m:t></m:r><m:r><m:t>a&lt;b &amp; c</m:t></m:r></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:acc><m:accPr><m:chr m:val="&#x20D7;"/></m:accPr><m:e><m:r><m:t>v</m:t></m:r></m:e></m:acc><m:bar><m:barPr><m:pos m:val="top"/></m:barPr><m:e><m:r><m:t>y</m:t></m:r></m:e></m:bar></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:groupChr><m:groupChrPr><m:chr m:val="⏞"/><m:pos m:val="top"/><m:vertJc m:val="bot"/></m:groupChrPr><m:e><m:r><m:t>x</m:t></m:r></m:e></m:groupChr><m:limUpp><m:e><m:r><m:t>x</m:t></m:r></m:e><m:lim><m:r><m:t>y</m:t></m:r></m:lim></m:limUpp></m:oMath>"#,
+        );
+        assert_round_trip(
+            r#"<m:oMath><m:func><m:fName><m:r><m:rPr><m:sty m:val="p"/></m:rPr><m:t>sin</m:t></m:r></m:fName><m:e><m:r><m:t>x</m:t></m:r></m:e></m:func><m:phant><m:e><m:r><m:t>y</m:t></m:r></m:e></m:phant><m:eqArr><m:e><m:r><m:t>a</m:t></m:r></m:e><m:e><m:r><m:t>b</m:t></m:r></m:e></m:eqArr></m:oMath>"#,
+        );
+    }
+
+    #[test]
+    fn test_write_from_latex() {
+        let formula = Formula::new();
+        let nodes = LatexParser::new(formula.arena())
+            .parse(r"\sum_{i=1}^{n} \frac{\alpha}{2} + \lim_{n \to \infty} a_n + \hat{x}")
+            .unwrap();
+        let written = write(&nodes);
+
+        let parsed = OmmlParser::new(formula.arena()).parse(&written).unwrap();
+        let mut parsed = parsed.iter();
+        assert!(matches!(
+            parsed.next(),
+            Some(MathNode::LargeOp {
+                operator: LargeOperator::Sum,
+                integrand: Some(integrand),
+                ..
+            }) if matches!(integrand.as_slice(), [MathNode::Frac { .. }])
+        ));
+        assert_eq!(parsed.next(), Some(&MathNode::Text(Cow::Borrowed("+"))));
+        assert!(matches!(
+            parsed.next(),
+            Some(MathNode::LargeOp {
+                operator: LargeOperator::Limit,
+                integrand: Some(integrand),
+                ..
+            }) if matches!(integrand.as_slice(), [MathNode::Sub { .. }])
+        ));
+        assert_eq!(parsed.next(), Some(&MathNode::Text(Cow::Borrowed("+"))));
+        assert!(matches!(
+            parsed.next(),
+            Some(MathNode::Accent {
+                accent: AccentType::Hat,
+                ..
+            })
+        ));
+        assert_eq!(parsed.next(), None);
+    }
+}
@@ -0,0 +1,125 @@
+/// Configuration types for HTML export.
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// The default location of the reveal.js distribution files.
+const DEFAULT_REVEAL_URL: &str = "https://cdn.jsdelivr.net/npm/reveal.js@5/dist";
+
+/// Options for [`Presentation::to_html`](crate::Presentation::to_html).
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::html::{HtmlOptions, HtmlStyle};
+///
+/// // reveal.js deck with pictures embedded as data URLs
+/// let options = HtmlOptions::default();
+///
+/// // Plain HTML, pictures written next to the page, first 10 slides only
+/// let options = HtmlOptions::new()
+///     .with_style(HtmlStyle::Plain)
+///     .with_image_dir("images")
+///     .with_max_slides(10);
+/// ```
+#[derive(Debug, Clone)]
+pub struct HtmlOptions {
+    /// Structure of the generated page
+    pub style: HtmlStyle,
+    /// Directory to write pictures into, linked from the output; pictures
+    /// are embedded as data URLs when `None`
+    pub image_dir: Option<Arc<Path>>,
+    /// Maximum number of slides to export, from the first
+    pub max_slides: Option<usize>,
+    /// URL of the reveal.js `dist` directory, for [`HtmlStyle::RevealJs`]
+    pub reveal_url: String,
+}
+
+impl Default for HtmlOptions {
+    fn default() -> Self {
+        Self {
+            style: HtmlStyle::RevealJs,
+            image_dir: None,
+            max_slides: None,
+            reveal_url: DEFAULT_REVEAL_URL.to_string(),
+        }
+    }
+}
+
+impl HtmlOptions {
+    /// Create options for a reveal.js deck with embedded pictures.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the structure of the generated page.
+    #[inline]
+    pub fn with_style(mut self, style: HtmlStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Write pictures into a directory instead of embedding them.
+    ///
+    /// Each picture is written to `dir` as `image_001.png`, `image_002.jpeg`
+    /// and so on, and referenced by a path built from `dir` as given, so pass
+    /// a path relative to where the HTML will be saved. Identical pictures
+    /// are written once. The directory is created if needed.
+    #[inline]
+    pub fn with_image_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.image_dir = Some(Arc::from(dir.into()));
+        self
+    }
+
+    /// Export at most `count` slides.
+    #[inline]
+    pub fn with_max_slides(mut self, count: usize) -> Self {
+        self.max_slides = Some(count);
+        self
+    }
+
+    /// Load reveal.js from another location, such as a local copy of its
+    /// `dist` directory, instead of the jsDelivr CDN.
+    #[inline]
+    pub fn with_reveal_url(mut self, url: impl Into<String>) -> Self {
+        self.reveal_url = url.into();
+        self
+    }
+}
+
+/// Structure of the generated HTML page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HtmlStyle {
+    /// A reveal.js presentation: the slides inside `.reveal > .slides`,
+    /// with reveal.js loaded from [`HtmlOptions::reveal_url`] and sized to
+    /// the slide size of the deck.
+    #[default]
+    RevealJs,
+
+    /// A standalone page without scripts, showing the slides one after
+    /// another with their notes below them.
+    Plain,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_html_options_builder() {
+        let options = HtmlOptions::new()
+            .with_style(HtmlStyle::Plain)
+            .with_image_dir("images")
+            .with_max_slides(3)
+            .with_reveal_url("reveal/dist");
+
+        assert_eq!(options.style, HtmlStyle::Plain);
+        assert_eq!(options.image_dir.as_deref(), Some(Path::new("images")));
+        assert_eq!(options.max_slides, Some(3));
+        assert_eq!(options.reveal_url, "reveal/dist");
+
+        let options = HtmlOptions::default();
+        assert_eq!(options.style, HtmlStyle::RevealJs);
+        assert!(options.image_dir.is_none());
+        assert!(options.max_slides.is_none());
+    }
+}
@@ -0,0 +1,40 @@
+//! HTML export of presentations.
+//!
+//! [`Presentation::to_html`](crate::Presentation::to_html) converts a deck
+//! into a single HTML file, either as a [reveal.js](https://revealjs.com)
+//! presentation or as plain HTML with the slides one after another. Each
+//! slide becomes a `<section>` in which every shape is an absolutely
+//! positioned `<div>`, placed and sized from the shape geometry stored in
+//! the file. Shape text keeps its font size, color, bold, italic and
+//! underline, pictures are embedded as data URLs or written to a directory,
+//! tables become HTML tables, and speaker notes go into
+//! `<aside class="notes">`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use litchi::Presentation;
+//! use litchi::html::{HtmlOptions, HtmlStyle};
+//!
+//! let pres = Presentation::open("presentation.pptx")?;
+//! let options = HtmlOptions::new()
+//!     .with_style(HtmlStyle::RevealJs)
+//!     .with_max_slides(20);
+//! std::fs::write("presentation.html", pres.to_html(&options)?)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Fidelity
+//!
+//! Positions, sizes, rotation and fill colors come from the file, so
+//! the layout matches the original, but text is laid out by the browser.
+//! Text formatting inherited from layouts and masters is not resolved, group
+//! shapes are placed as a whole, and .ppt files take text formatting from
+//! the whole text box. Speaker notes are read from .pptx files only;
+//! .ppt pictures need the `imgconv` feature. Keynote and ODP slides,
+//! which have no shape geometry, are exported as their text.
+
+mod config;
+mod presentation;
+
+pub use config::{HtmlOptions, HtmlStyle};
@@ -0,0 +1,432 @@
+/// HTML export of presentations.
+///
+/// Slides are read with their shape geometry through
+/// [`Presentation::extract_content_for_html`] and written as absolutely
+/// positioned elements, in CSS pixels at 96 per inch.
+use super::config::{HtmlOptions, HtmlStyle};
+use crate::common::xml::escape_xml;
+use crate::common::{Rect, Result};
+use crate::document::displayable;
+use crate::presentation::{PositionedShape, PositionedSlide, Presentation, SlideTable, StyledRun};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// Slide size of PowerPoint's 4:3 default (10 x 7.5 inches), for formats
+/// that do not store one.
+const DEFAULT_SLIDE_SIZE: (i64, i64) = (9_144_000, 6_858_000);
+
+/// EMUs per CSS pixel
+const EMUS_PER_PX: f64 = 9525.0;
+
+/// Styles shared by both page structures.
+const SLIDE_CSS: &str = "\
+.slide-canvas { position: relative; overflow: hidden; margin: 0 auto; text-align: left; }
+.shape { position: absolute; box-sizing: border-box; overflow: visible; line-height: 1.2; }
+.shape p { margin: 0; }
+.shape img { display: block; width: 100%; height: 100%; }
+.shape table { border-collapse: collapse; width: 100%; height: 100%; }
+.shape td { border: 1px solid #808080; padding: 2px 6px; vertical-align: top; }
+";
+
+/// Styles of the plain page structure.
+const PLAIN_CSS: &str = "\
+body { margin: 0; padding: 24px 0; background: #e8e8e8; font-family: Calibri, Arial, sans-serif; color: #000; }
+section.slide { margin: 0 auto 32px; width: fit-content; }
+section.slide .slide-canvas { background: #fff; box-shadow: 0 1px 4px rgba(0, 0, 0, 0.3); }
+aside.notes { margin-top: 8px; padding: 8px 12px; background: #fffbe6; font-size: 14px; }
+aside.notes p { margin: 0; }
+";
+
+impl Presentation {
+    /// Convert the presentation to a self-contained HTML deck.
+    ///
+    /// Every slide becomes a `<section>`, with each shape placed at the
+    /// position and size stored in the file. See the [`html`](crate::html)
+    /// module for what is kept of the slides.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the slides cannot be read, or a picture cannot be written
+    /// to [`HtmlOptions::image_dir`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    /// use litchi::html::{HtmlOptions, HtmlStyle};
+    ///
+    /// let pres = Presentation::open("presentation.ppt")?;
+    /// let options = HtmlOptions::new().with_style(HtmlStyle::Plain);
+    /// std::fs::write("presentation.html", pres.to_html(&options)?)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_html(&self, options: &HtmlOptions) -> Result<String> {
+        let (width, height) = self
+            .slide_size()?
+            .map(|(width, height)| (width.emus(), height.emus()))
+            .unwrap_or(DEFAULT_SLIDE_SIZE);
+        let slides = self.extract_content_for_html(options.max_slides)?;
+        let title = self
+            .metadata()?
+            .and_then(|metadata| metadata.title)
+            .filter(|title| !title.trim().is_empty())
+            .unwrap_or_else(|| "Presentation".to_string());
+
+        let mut images = ImageSink::new(options.image_dir.as_deref());
+        let mut html = String::new();
+        html.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        html.push_str("<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n");
+        let _ = writeln!(html, "<title>{}</title>", escape_xml(&title));
+        let reveal_url = escape_xml(options.reveal_url.trim_end_matches('/'));
+        if options.style == HtmlStyle::RevealJs {
+            let _ = writeln!(
+                html,
+                "<link rel=\"stylesheet\" href=\"{}/reveal.css\">",
+                reveal_url
+            );
+        }
+        html.push_str("<style>\n");
+        html.push_str(SLIDE_CSS);
+        if options.style == HtmlStyle::Plain {
+            html.push_str(PLAIN_CSS);
+        }
+        html.push_str("</style>\n</head>\n<body>\n");
+
+        match options.style {
+            HtmlStyle::RevealJs => {
+                html.push_str("<div class=\"reveal\">\n<div class=\"slides\">\n");
+                for slide in &slides {
+                    let _ = writeln!(html, "<section data-slide=\"{}\">", slide.number);
+                    write_slide(&mut html, slide, (width, height), &mut images)?;
+                    html.push_str("</section>\n");
+                }
+                html.push_str("</div>\n</div>\n");
+                let _ = writeln!(html, "<script src=\"{}/reveal.js\"></script>", reveal_url);
+                let _ = writeln!(
+                    html,
+                    "<script>Reveal.initialize({{ width: {}, height: {}, margin: 0, center: false }});</script>",
+                    px(width),
+                    px(height)
+                );
+            },
+            HtmlStyle::Plain => {
+                for slide in &slides {
+                    let _ = writeln!(
+                        html,
+                        "<section class=\"slide\" data-slide=\"{}\">",
+                        slide.number
+                    );
+                    write_slide(&mut html, slide, (width, height), &mut images)?;
+                    html.push_str("</section>\n");
+                }
+            },
+        }
+
+        html.push_str("</body>\n</html>\n");
+        Ok(html)
+    }
+}
+
+/// Write the shapes of a slide into a canvas of the slide size, followed
+/// by the speaker notes.
+fn write_slide(
+    html: &mut String,
+    slide: &PositionedSlide,
+    (width, height): (i64, i64),
+    images: &mut ImageSink,
+) -> Result<()> {
+    let _ = writeln!(
+        html,
+        "<div class=\"slide-canvas\" style=\"width:{}px;height:{}px\">",
+        px(width),
+        px(height)
+    );
+    for shape in &slide.shapes {
+        write_shape(html, shape, images)?;
+    }
+    html.push_str("</div>\n");
+
+    if let Some(notes) = slide
+        .notes
+        .as_deref()
+        .filter(|notes| !notes.trim().is_empty())
+    {
+        html.push_str("<aside class=\"notes\">");
+        for line in notes.lines() {
+            let _ = write!(html, "<p>{}</p>", escape_xml(line));
+        }
+        html.push_str("</aside>\n");
+    }
+    Ok(())
+}
+
+/// Write a shape as a `div`, absolutely positioned if it has bounds.
+///
+/// Shapes without text, picture, table or fill are left out, since they
+/// would not show.
+fn write_shape(html: &mut String, shape: &PositionedShape, images: &mut ImageSink) -> Result<()> {
+    let has_text = shape
+        .paragraphs
+        .iter()
+        .flatten()
+        .any(|run| !run.text.trim().is_empty());
+    let filled = shape.fill.is_some() && shape.bounds.is_some();
+    if !has_text && shape.image.is_none() && shape.table.is_none() && !filled {
+        return Ok(());
+    }
+
+    let mut style = String::new();
+    match shape.bounds {
+        Some(bounds) => push_frame_style(&mut style, &bounds),
+        // Shapes without a position flow from the top of the slide
+        None => style.push_str("position:static;"),
+    }
+    if shape.rotation != 0.0 {
+        let _ = write!(style, "transform:rotate({}deg);", round2(shape.rotation));
+    }
+    if let Some(fill) = shape.fill {
+        let _ = write!(style, "background-color:{};", fill.to_hex_string());
+    }
+    let _ = write!(html, "<div class=\"shape\" style=\"{}\">", style);
+
+    if let Some((data, extension)) = &shape.image {
+        if let Some(src) = images.source(data, extension)? {
+            // Pictures are mirrored by their flips; text is not
+            let flip = match (shape.flip_horizontal, shape.flip_vertical) {
+                (false, false) => "",
+                (true, false) => " style=\"transform:scaleX(-1)\"",
+                (false, true) => " style=\"transform:scaleY(-1)\"",
+                (true, true) => " style=\"transform:scale(-1,-1)\"",
+            };
+            let _ = write!(html, "<img src=\"{}\" alt=\"\"{}>", escape_xml(&src), flip);
+        }
+    } else if let Some(table) = &shape.table {
+        write_table(html, table);
+    } else if has_text {
+        for paragraph in &shape.paragraphs {
+            write_paragraph(html, paragraph);
+        }
+    }
+    html.push_str("</div>\n");
+    Ok(())
+}
+
+/// Position and size of a shape frame.
+fn push_frame_style(style: &mut String, bounds: &Rect) {
+    let _ = write!(
+        style,
+        "left:{}px;top:{}px;width:{}px;height:{}px;",
+        px(bounds.x().emus()),
+        px(bounds.y().emus()),
+        px(bounds.width().emus()),
+        px(bounds.height().emus())
+    );
+}
+
+/// Write a paragraph of styled runs. Empty paragraphs keep their line.
+fn write_paragraph(html: &mut String, runs: &[StyledRun]) {
+    html.push_str("<p>");
+    if runs.iter().all(|run| run.text.is_empty()) {
+        html.push_str("<br>");
+    }
+    for run in runs.iter().filter(|run| !run.text.is_empty()) {
+        let text = escape_xml(&run.text).replace('\n', "<br>");
+        let style = run_style(run);
+        if style.is_empty() {
+            html.push_str(&text);
+        } else {
+            let _ = write!(html, "<span style=\"{}\">{}</span>", style, text);
+        }
+    }
+    html.push_str("</p>");
+}
+
+/// Inline CSS for the formatting of a run.
+fn run_style(run: &StyledRun) -> String {
+    let mut style = String::new();
+    if let Some(size) = run.font_size {
+        let _ = write!(style, "font-size:{}pt;", round2(size));
+    }
+    if let Some(color) = run.color {
+        let _ = write!(style, "color:{};", color.to_hex_string());
+    }
+    if run.bold {
+        style.push_str("font-weight:bold;");
+    }
+    if run.italic {
+        style.push_str("font-style:italic;");
+    }
+    if run.underline {
+        style.push_str("text-decoration:underline;");
+    }
+    style
+}
+
+/// Write a slide table, with merged cells spanning rows and columns.
+fn write_table(html: &mut String, table: &SlideTable) {
+    html.push_str("<table>");
+    for row in table.rows() {
+        html.push_str("<tr>");
+        for cell in row.iter().filter(|cell| !cell.is_merged()) {
+            html.push_str("<td");
+            if cell.col_span() > 1 {
+                let _ = write!(html, " colspan=\"{}\"", cell.col_span());
+            }
+            if cell.row_span() > 1 {
+                let _ = write!(html, " rowspan=\"{}\"", cell.row_span());
+            }
+            html.push('>');
+            html.push_str(&escape_xml(cell.text()).replace('\n', "<br>"));
+            html.push_str("</td>");
+        }
+        html.push_str("</tr>");
+    }
+    html.push_str("</table>");
+}
+
+/// Where pictures go: data URLs, or files in a directory.
+struct ImageSink<'a> {
+    dir: Option<&'a Path>,
+    /// Path of the file written for each picture content hash
+    files: HashMap<[u8; 32], String>,
+}
+
+impl<'a> ImageSink<'a> {
+    fn new(dir: Option<&'a Path>) -> Self {
+        Self {
+            dir,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Get the `src` of a picture, converting metafiles to PNG. Pictures
+    /// that browsers cannot show are left out.
+    fn source(&mut self, data: &[u8], extension: &'static str) -> Result<Option<String>> {
+        let Some(dir) = self.dir else {
+            let (data, extension) = displayable(data.to_vec(), extension);
+            return Ok(mime_type(extension)
+                .map(|mime| format!("data:{};base64,{}", mime, BASE64.encode(data))));
+        };
+
+        let hash: [u8; 32] = Sha256::digest(data).into();
+        if let Some(path) = self.files.get(&hash) {
+            return Ok(Some(path.clone()));
+        }
+        let (data, extension) = displayable(data.to_vec(), extension);
+        if mime_type(extension).is_none() {
+            return Ok(None);
+        }
+        if self.files.is_empty() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let name = format!("image_{:03}.{}", self.files.len() + 1, extension);
+        std::fs::write(dir.join(&name), data)?;
+
+        let mut path = dir.join(&name).to_string_lossy().into_owned();
+        if cfg!(windows) {
+            path = path.replace('\\', "/");
+        }
+        self.files.insert(hash, path.clone());
+        Ok(Some(path))
+    }
+}
+
+/// MIME type of the picture formats browsers can show.
+fn mime_type(extension: &str) -> Option<&'static str> {
+    match extension {
+        "png" => Some("image/png"),
+        "jpg" | "jpeg" => Some("image/jpeg"),
+        "gif" => Some("image/gif"),
+        "bmp" => Some("image/bmp"),
+        "svg" => Some("image/svg+xml"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Convert EMUs to CSS pixels.
+fn px(emus: i64) -> f64 {
+    round2(emus as f64 / EMUS_PER_PX)
+}
+
+/// Round to two decimal places, which is enough for CSS.
+fn round2(value: f64) -> f64 {
+    (value * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_pptx_to_reveal_js() {
+        let pres = Presentation::open("test-data/ooxml/pptx/sample.pptx").unwrap();
+        let html = pres.to_html(&HtmlOptions::new()).unwrap();
+
+        assert!(html.contains("<div class=\"reveal\">"));
+        assert!(html.contains("Reveal.initialize({ width: 960, height: 720"));
+        assert_eq!(
+            html.matches("<section data-slide=").count(),
+            pres.slide_count().unwrap()
+        );
+        // Title placeholder placed from the slide layout
+        assert!(html.contains("left:72px;top:223.67px;width:816px;height:154.33px;"));
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_pptx_pictures_as_data_urls() {
+        let pres = Presentation::open("test-data/ooxml/pptx/shapes.pptx").unwrap();
+        let html = pres.to_html(&HtmlOptions::new()).unwrap();
+        assert!(html.contains("<img src=\"data:image/jpeg;base64,"));
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_pictures_written_to_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let image_dir = dir.path().join("images");
+        let pres = Presentation::open("test-data/ooxml/pptx/shapes.pptx").unwrap();
+        let html = pres
+            .to_html(&HtmlOptions::new().with_image_dir(&image_dir))
+            .unwrap();
+
+        assert!(!html.contains("data:image/"));
+        assert!(image_dir.join("image_001.jpeg").exists());
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_ppt_to_plain_html() {
+        let pres = Presentation::open("test-data/ole/ppt/SampleShow.ppt").unwrap();
+        let options = HtmlOptions::new()
+            .with_style(HtmlStyle::Plain)
+            .with_max_slides(1);
+        let html = pres.to_html(&options).unwrap();
+
+        assert!(!html.contains("reveal"));
+        assert_eq!(html.matches("<section class=\"slide\"").count(), 1);
+        assert!(html.contains("<div class=\"shape\" style=\"left:"));
+    }
+
+    #[test]
+    fn test_run_style() {
+        let run = StyledRun {
+            text: "x".to_string(),
+            font_size: Some(18.0),
+            bold: true,
+            underline: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            run_style(&run),
+            "font-size:18pt;font-weight:bold;text-decoration:underline;"
+        );
+        assert_eq!(px(914_400), 96.0);
+    }
+}
@@ -14,6 +14,7 @@ use crate::{
 };
 use crate::{
     common::error::{Error, Result},
+    common::trace,
     ole::escher::EscherRecordType,
 };
 use std::borrow::Cow;
@@ -220,6 +221,15 @@ impl<'data> MetafileBlip<'data> {
         let filter = data[offset + 33];
         offset += 34;
 
+        // Anything other than deflate or "none" is read as uncompressed data
+        if compression != 0 && compression != 0xFE {
+            trace::warn!(
+                compression,
+                size = compressed_size,
+                "unknown metafile BLIP compression flag, treating data as uncompressed"
+            );
+        }
+
         // Extract picture data (zero-copy borrow)
         // Use the remaining data as the picture data, up to compressed_size
         let pic_data_len = compressed_size as usize;
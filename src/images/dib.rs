@@ -0,0 +1,647 @@
+//! Device Independent Bitmap (DIB) decoding
+//!
+//! Metafiles embed raster images as DIBs: a `BITMAPINFO` header (optionally
+//! followed by color masks and a color table) plus the pixel bits. WMF records
+//! carry a packed DIB where the bits directly follow the header, while EMF
+//! records store the header and the bits at separate offsets.
+//!
+//! Supported variants:
+//! - `BITMAPCOREHEADER` and `BITMAPINFOHEADER` (including the V4/V5 extensions)
+//! - 1, 4, 8, 16, 24 and 32 bits per pixel
+//! - `BI_RGB`, `BI_RLE8`, `BI_RLE4`, `BI_BITFIELDS`, `BI_JPEG` and `BI_PNG`
+//! - Bottom-up (positive height) and top-down (negative height) row order
+//!
+//! # References
+//!
+//! - [MS-WMF] 2.2.2.9 DeviceIndependentBitmap Object
+//! - [MS-WMF] 2.1.1.7 Compression Enumeration
+
+use crate::common::error::{Error, Result};
+use image::{ImageFormat, Rgba, RgbaImage};
+use std::io::Cursor;
+
+const BI_RGB: u32 = 0;
+const BI_RLE8: u32 = 1;
+const BI_RLE4: u32 = 2;
+const BI_BITFIELDS: u32 = 3;
+const BI_JPEG: u32 = 4;
+const BI_PNG: u32 = 5;
+const BI_ALPHABITFIELDS: u32 = 6;
+
+/// Size of `BITMAPCOREHEADER`
+const CORE_HEADER_SIZE: usize = 12;
+/// Size of `BITMAPINFOHEADER`
+const INFO_HEADER_SIZE: usize = 40;
+
+/// Largest accepted dimension, guarding against corrupt headers
+const MAX_DIMENSION: u32 = 1 << 15;
+
+/// Decoded DIB header fields
+#[derive(Debug, Clone)]
+struct DibHeader {
+    width: u32,
+    height: u32,
+    bottom_up: bool,
+    bit_count: u16,
+    compression: u32,
+    /// Color masks (red, green, blue, alpha) for 16/32-bit images
+    masks: [u32; 4],
+    palette: Vec<Rgba<u8>>,
+    /// Size of the header, masks and color table in bytes
+    info_size: usize,
+}
+
+/// A decoded Device Independent Bitmap
+#[derive(Debug, Clone)]
+pub struct Dib {
+    image: RgbaImage,
+    bottom_up: bool,
+}
+
+impl Dib {
+    /// Decode a packed DIB, where the pixel bits directly follow the
+    /// header, masks and color table (as embedded in WMF records).
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let header = DibHeader::parse(data)?;
+        let bits = &data[header.info_size.min(data.len())..];
+        Self::decode(&header, bits)
+    }
+
+    /// Decode a DIB whose `BITMAPINFO` and pixel bits are stored separately
+    /// (as in EMF bitmap records).
+    pub fn parse_parts(info: &[u8], bits: &[u8]) -> Result<Self> {
+        let header = DibHeader::parse(info)?;
+        Self::decode(&header, bits)
+    }
+
+    /// Width in pixels
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    /// Height in pixels
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    /// Whether the bits were stored bottom-up (positive height)
+    pub fn is_bottom_up(&self) -> bool {
+        self.bottom_up
+    }
+
+    /// Decoded pixels, top row first
+    pub fn image(&self) -> &RgbaImage {
+        &self.image
+    }
+
+    /// Consume the bitmap and return its pixels, top row first
+    pub fn into_image(self) -> RgbaImage {
+        self.image
+    }
+
+    /// Extract a source rectangle given with a top-left origin, as used by
+    /// the `BitBlt`/`StretchBlt` family.
+    ///
+    /// The rectangle is clipped to the bitmap; `None` is returned when
+    /// nothing remains.
+    pub fn region(&self, x: i32, y: i32, width: i32, height: i32) -> Option<RgbaImage> {
+        let (x0, x1) = clip_span(x, width, self.width())?;
+        let (y0, y1) = clip_span(y, height, self.height())?;
+        if x0 == 0 && y0 == 0 && x1 == self.width() && y1 == self.height() {
+            return Some(self.image.clone());
+        }
+        Some(image::imageops::crop_imm(&self.image, x0, y0, x1 - x0, y1 - y0).to_image())
+    }
+
+    /// Extract a source rectangle given in DIB coordinates, as used by
+    /// `StretchDIBits`: for bottom-up bitmaps the origin is the lower-left
+    /// corner.
+    pub fn dib_region(&self, x: i32, y: i32, width: i32, height: i32) -> Option<RgbaImage> {
+        if self.bottom_up {
+            let top = self.height() as i64 - y as i64 - height as i64;
+            let top = top.clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+            self.region(x, top, width, height)
+        } else {
+            self.region(x, y, width, height)
+        }
+    }
+
+    fn decode(header: &DibHeader, bits: &[u8]) -> Result<Self> {
+        let image = match header.compression {
+            BI_JPEG | BI_PNG => image::load_from_memory(bits)
+                .map_err(|e| Error::ParseError(format!("Failed to decode DIB image: {}", e)))?
+                .to_rgba8(),
+            BI_RLE8 | BI_RLE4 => decode_rle(header, bits)?,
+            BI_RGB | BI_BITFIELDS | BI_ALPHABITFIELDS => decode_uncompressed(header, bits)?,
+            other => {
+                return Err(Error::ParseError(format!(
+                    "Unsupported DIB compression: {}",
+                    other
+                )));
+            },
+        };
+
+        Ok(Self {
+            image,
+            bottom_up: header.bottom_up,
+        })
+    }
+}
+
+impl DibHeader {
+    fn parse(data: &[u8]) -> Result<Self> {
+        let header_size = read_u32(data, 0)
+            .ok_or_else(|| Error::ParseError("DIB header too short".into()))?
+            as usize;
+
+        let (width, height, bit_count, compression, colors_used, mut offset) =
+            if header_size == CORE_HEADER_SIZE {
+                let width = read_u16(data, 4).unwrap_or(0) as i64;
+                let height = read_u16(data, 6).unwrap_or(0) as i64;
+                let bit_count = read_u16(data, 10).unwrap_or(0);
+                (width, height, bit_count, BI_RGB, 0, CORE_HEADER_SIZE)
+            } else if header_size >= INFO_HEADER_SIZE && data.len() >= INFO_HEADER_SIZE {
+                let width = read_i32(data, 4).unwrap_or(0) as i64;
+                let height = read_i32(data, 8).unwrap_or(0) as i64;
+                let bit_count = read_u16(data, 14).unwrap_or(0);
+                let compression = read_u32(data, 16).unwrap_or(0);
+                let colors_used = read_u32(data, 32).unwrap_or(0);
+                (
+                    width,
+                    height,
+                    bit_count,
+                    compression,
+                    colors_used,
+                    header_size,
+                )
+            } else {
+                return Err(Error::ParseError(format!(
+                    "Unsupported DIB header size: {}",
+                    header_size
+                )));
+            };
+
+        let bottom_up = height > 0;
+        let (width, height) = (width.unsigned_abs(), height.unsigned_abs());
+        if width == 0
+            || height == 0
+            || width > MAX_DIMENSION as u64
+            || height > MAX_DIMENSION as u64
+        {
+            return Err(Error::ParseError(format!(
+                "Invalid DIB dimensions: {}x{}",
+                width, height
+            )));
+        }
+
+        // Color masks live inside V2+ headers, or directly after a plain
+        // BITMAPINFOHEADER when BI_BITFIELDS is used.
+        let mut masks = match bit_count {
+            16 => [0x7C00, 0x03E0, 0x001F, 0],
+            _ => [0x00FF_0000, 0x0000_FF00, 0x0000_00FF, 0],
+        };
+        if matches!(compression, BI_BITFIELDS | BI_ALPHABITFIELDS) {
+            let mask_count = if compression == BI_ALPHABITFIELDS {
+                4
+            } else {
+                3
+            };
+            for (i, mask) in masks.iter_mut().take(mask_count).enumerate() {
+                *mask = read_u32(data, INFO_HEADER_SIZE + i * 4).unwrap_or(0);
+            }
+            if header_size >= 56 {
+                masks[3] = read_u32(data, 52).unwrap_or(0);
+            }
+            if header_size == INFO_HEADER_SIZE {
+                offset += mask_count * 4;
+            }
+        }
+
+        let palette_len = if colors_used > 0 {
+            (colors_used as usize).min(256)
+        } else if bit_count <= 8 {
+            1 << bit_count
+        } else {
+            0
+        };
+        let entry_size = if header_size == CORE_HEADER_SIZE {
+            3
+        } else {
+            4
+        };
+        let mut palette = Vec::with_capacity(palette_len);
+        for i in 0..palette_len {
+            let at = offset + i * entry_size;
+            match data.get(at..at + 3) {
+                Some(bgr) => palette.push(Rgba([bgr[2], bgr[1], bgr[0], 255])),
+                None => break,
+            }
+        }
+        offset += colors_used.max(palette_len as u32) as usize * entry_size;
+
+        Ok(Self {
+            width: width as u32,
+            height: height as u32,
+            bottom_up,
+            bit_count,
+            compression,
+            masks,
+            palette,
+            info_size: offset,
+        })
+    }
+
+    fn palette_color(&self, index: usize) -> Rgba<u8> {
+        self.palette
+            .get(index)
+            .copied()
+            .unwrap_or(Rgba([0, 0, 0, 255]))
+    }
+
+    /// Image row for the `n`th stored scanline
+    fn image_row(&self, n: u32) -> u32 {
+        if self.bottom_up {
+            self.height - 1 - n
+        } else {
+            n
+        }
+    }
+}
+
+/// Decode `BI_RGB` and `BI_BITFIELDS` pixel data
+fn decode_uncompressed(header: &DibHeader, bits: &[u8]) -> Result<RgbaImage> {
+    let bpp = header.bit_count as usize;
+    if !matches!(bpp, 1 | 4 | 8 | 16 | 24 | 32) {
+        return Err(Error::ParseError(format!(
+            "Unsupported DIB bit count: {}",
+            bpp
+        )));
+    }
+
+    let stride = (header.width as usize * bpp).div_ceil(32) * 4;
+    let mut image = RgbaImage::new(header.width, header.height);
+    let channels = header.masks.map(MaskChannel::new);
+
+    for n in 0..header.height {
+        let start = n as usize * stride;
+        let Some(row) = bits.get(start..start + stride) else {
+            break;
+        };
+        let y = header.image_row(n);
+
+        for x in 0..header.width {
+            let col = x as usize;
+            let pixel = match bpp {
+                1 => header.palette_color(((row[col / 8] >> (7 - col % 8)) & 1) as usize),
+                4 => {
+                    let byte = row[col / 2];
+                    let index = if col.is_multiple_of(2) {
+                        byte >> 4
+                    } else {
+                        byte & 0x0F
+                    };
+                    header.palette_color(index as usize)
+                },
+                8 => header.palette_color(row[col] as usize),
+                16 => {
+                    let value = u16::from_le_bytes([row[col * 2], row[col * 2 + 1]]) as u32;
+                    masked_color(&channels, value)
+                },
+                24 => Rgba([row[col * 3 + 2], row[col * 3 + 1], row[col * 3], 255]),
+                _ => {
+                    let at = col * 4;
+                    let value =
+                        u32::from_le_bytes([row[at], row[at + 1], row[at + 2], row[at + 3]]);
+                    masked_color(&channels, value)
+                },
+            };
+            image.put_pixel(x, y, pixel);
+        }
+    }
+
+    Ok(image)
+}
+
+/// Decode `BI_RLE8` and `BI_RLE4` pixel data
+///
+/// Pixels skipped by delta or end-of-line escapes are left transparent.
+fn decode_rle(header: &DibHeader, bits: &[u8]) -> Result<RgbaImage> {
+    let nibbles = header.compression == BI_RLE4;
+    if header.bit_count != if nibbles { 4 } else { 8 } {
+        return Err(Error::ParseError(format!(
+            "RLE compression with {} bits per pixel",
+            header.bit_count
+        )));
+    }
+
+    let mut image = RgbaImage::new(header.width, header.height);
+    let (mut x, mut line) = (0u32, 0u32);
+    let mut put = |x: &mut u32, line: u32, index: u8| {
+        if *x < header.width && line < header.height {
+            image.put_pixel(
+                *x,
+                header.image_row(line),
+                header.palette_color(index as usize),
+            );
+        }
+        *x += 1;
+    };
+
+    let mut pos = 0;
+    while pos + 1 < bits.len() {
+        let (count, value) = (bits[pos], bits[pos + 1]);
+        pos += 2;
+
+        if count > 0 {
+            // Encoded run
+            for i in 0..count {
+                let index = if !nibbles {
+                    value
+                } else if i.is_multiple_of(2) {
+                    value >> 4
+                } else {
+                    value & 0x0F
+                };
+                put(&mut x, line, index);
+            }
+            continue;
+        }
+
+        match value {
+            0 => {
+                x = 0;
+                line += 1;
+            },
+            1 => break,
+            2 => {
+                let (Some(&dx), Some(&dy)) = (bits.get(pos), bits.get(pos + 1)) else {
+                    break;
+                };
+                pos += 2;
+                x += dx as u32;
+                line += dy as u32;
+            },
+            n => {
+                // Absolute run, padded to a 16-bit boundary
+                let len = if nibbles {
+                    (n as usize).div_ceil(2)
+                } else {
+                    n as usize
+                };
+                let Some(run) = bits.get(pos..pos + len) else {
+                    break;
+                };
+                for i in 0..n as usize {
+                    let index = if !nibbles {
+                        run[i]
+                    } else if i.is_multiple_of(2) {
+                        run[i / 2] >> 4
+                    } else {
+                        run[i / 2] & 0x0F
+                    };
+                    put(&mut x, line, index);
+                }
+                pos += len + len % 2;
+            },
+        }
+
+        if line >= header.height {
+            break;
+        }
+    }
+
+    Ok(image)
+}
+
+/// A single color channel described by a bit mask
+#[derive(Debug, Clone, Copy)]
+struct MaskChannel {
+    mask: u32,
+    shift: u32,
+    max: u32,
+}
+
+impl MaskChannel {
+    fn new(mask: u32) -> Self {
+        let shift = if mask == 0 { 0 } else { mask.trailing_zeros() };
+        Self {
+            mask,
+            shift,
+            max: mask >> shift,
+        }
+    }
+
+    fn extract(&self, value: u32) -> Option<u8> {
+        if self.mask == 0 {
+            return None;
+        }
+        let raw = (value & self.mask) >> self.shift;
+        Some(((raw as u64 * 255 + self.max as u64 / 2) / self.max as u64) as u8)
+    }
+}
+
+fn masked_color(channels: &[MaskChannel; 4], value: u32) -> Rgba<u8> {
+    Rgba([
+        channels[0].extract(value).unwrap_or(0),
+        channels[1].extract(value).unwrap_or(0),
+        channels[2].extract(value).unwrap_or(0),
+        channels[3].extract(value).unwrap_or(255),
+    ])
+}
+
+/// Clip the span `[start, start + len)` to `[0, limit)`
+fn clip_span(start: i32, len: i32, limit: u32) -> Option<(u32, u32)> {
+    let lo = (start as i64).max(0);
+    let hi = (start as i64 + len as i64).min(limit as i64);
+    (hi > lo).then_some((lo as u32, hi as u32))
+}
+
+/// Whether a ternary raster operation reads the source bitmap
+///
+/// The operation index (bits 16-23) is a truth table over pattern, source
+/// and destination; it depends on the source when the entries for a set
+/// source bit (mask `0xCC`) differ from those for a clear one (`0x33`).
+pub fn rop_uses_source(rop: u32) -> bool {
+    let index = (rop >> 16) & 0xFF;
+    ((index & 0xCC) >> 2) != (index & 0x33)
+}
+
+/// Encode an image as PNG, e.g. for embedding in SVG output
+pub fn encode_png(image: &RgbaImage) -> Result<Vec<u8>> {
+    let mut buffer = Cursor::new(Vec::new());
+    image
+        .write_to(&mut buffer, ImageFormat::Png)
+        .map_err(|e| Error::ParseError(format!("Failed to encode image: {}", e)))?;
+    Ok(buffer.into_inner())
+}
+
+/// Draw `source` scaled into the destination rectangle of `canvas`.
+///
+/// A negative width or height mirrors the image, matching GDI's handling of
+/// negative extents. Sampling is nearest-neighbour and source pixels are
+/// alpha-blended over the canvas.
+pub fn draw_scaled(canvas: &mut RgbaImage, source: &RgbaImage, x: f64, y: f64, w: f64, h: f64) {
+    if w == 0.0 || h == 0.0 || source.width() == 0 || source.height() == 0 {
+        return;
+    }
+
+    let (left, right) = if w < 0.0 { (x + w, x) } else { (x, x + w) };
+    let (top, bottom) = if h < 0.0 { (y + h, y) } else { (y, y + h) };
+    let x0 = left.round().max(0.0) as u32;
+    let y0 = top.round().max(0.0) as u32;
+    let x1 = (right.round().max(0.0) as u32).min(canvas.width());
+    let y1 = (bottom.round().max(0.0) as u32).min(canvas.height());
+
+    let (sw, sh) = (source.width() as f64, source.height() as f64);
+    for py in y0..y1 {
+        let fy = (py as f64 + 0.5 - top) / (bottom - top);
+        let fy = if h < 0.0 { 1.0 - fy } else { fy };
+        let sy = ((fy * sh) as u32).min(source.height() - 1);
+        for px in x0..x1 {
+            let fx = (px as f64 + 0.5 - left) / (right - left);
+            let fx = if w < 0.0 { 1.0 - fx } else { fx };
+            let sx = ((fx * sw) as u32).min(source.width() - 1);
+
+            let src = source.get_pixel(sx, sy);
+            match src[3] {
+                0 => {},
+                255 => canvas.put_pixel(px, py, *src),
+                alpha => {
+                    let dst = canvas.get_pixel_mut(px, py);
+                    let a = alpha as u32;
+                    for c in 0..3 {
+                        dst[c] = ((src[c] as u32 * a + dst[c] as u32 * (255 - a)) / 255) as u8;
+                    }
+                    dst[3] = dst[3].max(alpha);
+                },
+            }
+        }
+    }
+}
+
+#[inline]
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+#[inline]
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[inline]
+fn read_i32(data: &[u8], offset: usize) -> Option<i32> {
+    read_u32(data, offset).map(|v| v as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a packed BITMAPINFOHEADER DIB
+    fn info_dib(
+        width: i32,
+        height: i32,
+        bpp: u16,
+        compression: u32,
+        palette: &[[u8; 4]],
+        bits: &[u8],
+    ) -> Vec<u8> {
+        let mut dib = Vec::new();
+        dib.extend_from_slice(&40u32.to_le_bytes());
+        dib.extend_from_slice(&width.to_le_bytes());
+        dib.extend_from_slice(&height.to_le_bytes());
+        dib.extend_from_slice(&1u16.to_le_bytes());
+        dib.extend_from_slice(&bpp.to_le_bytes());
+        dib.extend_from_slice(&compression.to_le_bytes());
+        dib.extend_from_slice(&(bits.len() as u32).to_le_bytes());
+        dib.extend_from_slice(&[0u8; 8]);
+        dib.extend_from_slice(&(palette.len() as u32).to_le_bytes());
+        dib.extend_from_slice(&0u32.to_le_bytes());
+        for entry in palette {
+            dib.extend_from_slice(entry);
+        }
+        dib.extend_from_slice(bits);
+        dib
+    }
+
+    #[test]
+    fn test_decode_24bit_bottom_up() {
+        // 2x2: bottom row blue, red; top row green, white (rows padded to 8 bytes)
+        let bits = [
+            255, 0, 0, 0, 0, 255, 0, 0, //
+            0, 255, 0, 255, 255, 255, 0, 0,
+        ];
+        let dib = Dib::parse(&info_dib(2, 2, 24, BI_RGB, &[], &bits)).unwrap();
+        assert!(dib.is_bottom_up());
+        assert_eq!(dib.image().get_pixel(0, 1), &Rgba([0, 0, 255, 255]));
+        assert_eq!(dib.image().get_pixel(1, 1), &Rgba([255, 0, 0, 255]));
+        assert_eq!(dib.image().get_pixel(0, 0), &Rgba([0, 255, 0, 255]));
+        assert_eq!(dib.image().get_pixel(1, 0), &Rgba([255, 255, 255, 255]));
+    }
+
+    #[test]
+    fn test_decode_8bit_top_down() {
+        let palette = [[0, 0, 255, 0], [0, 255, 0, 0]];
+        let bits = [0, 1, 0, 0, 1, 0, 0, 0];
+        let dib = Dib::parse(&info_dib(2, -2, 8, BI_RGB, &palette, &bits)).unwrap();
+        assert!(!dib.is_bottom_up());
+        assert_eq!(dib.image().get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+        assert_eq!(dib.image().get_pixel(1, 0), &Rgba([0, 255, 0, 255]));
+        assert_eq!(dib.image().get_pixel(0, 1), &Rgba([0, 255, 0, 255]));
+    }
+
+    #[test]
+    fn test_decode_rle8_and_rle4() {
+        let palette = [[0, 0, 0, 0], [0, 0, 255, 0], [255, 0, 0, 0]];
+        // Row 0: run of 3 x index 1, then absolute [2, 2, 1]; end of bitmap
+        let rle8 = [3, 1, 0, 3, 2, 2, 1, 0, 0, 0, 0, 1];
+        let dib = Dib::parse(&info_dib(6, 1, 8, BI_RLE8, &palette, &rle8)).unwrap();
+        let row: Vec<_> = (0..6).map(|x| dib.image().get_pixel(x, 0)[0]).collect();
+        assert_eq!(row, [255, 255, 255, 0, 0, 255]);
+
+        // Row 0: alternating 1/2 run of 4, end of line; row 1: run of 2 x index 2
+        let rle4 = [4, 0x12, 0, 0, 2, 0x22, 0, 1];
+        let dib = Dib::parse(&info_dib(4, 2, 4, BI_RLE4, &palette, &rle4)).unwrap();
+        let bottom: Vec<_> = (0..4).map(|x| dib.image().get_pixel(x, 1)[2]).collect();
+        assert_eq!(bottom, [0, 255, 0, 255]);
+        assert_eq!(dib.image().get_pixel(0, 0), &Rgba([0, 0, 255, 255]));
+        assert_eq!(dib.image().get_pixel(3, 0)[3], 0);
+    }
+
+    #[test]
+    fn test_dib_region_origin() {
+        let palette = [[0, 0, 255, 0], [0, 255, 0, 0]];
+        // Bottom row red, top row green
+        let bits = [0, 0, 0, 0, 1, 1, 0, 0];
+        let dib = Dib::parse(&info_dib(2, 2, 8, BI_RGB, &palette, &bits)).unwrap();
+
+        let bottom = dib.dib_region(0, 0, 2, 1).unwrap();
+        assert_eq!(bottom.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+        let top = dib.region(0, 0, 2, 1).unwrap();
+        assert_eq!(top.get_pixel(0, 0), &Rgba([0, 255, 0, 255]));
+        assert!(dib.region(5, 5, 1, 1).is_none());
+    }
+
+    #[test]
+    fn test_rop_uses_source() {
+        assert!(rop_uses_source(0x00CC0020)); // SRCCOPY
+        assert!(rop_uses_source(0x008800C6)); // SRCAND
+        assert!(!rop_uses_source(0x00F00021)); // PATCOPY
+        assert!(!rop_uses_source(0x00000042)); // BLACKNESS
+    }
+
+    #[test]
+    fn test_draw_scaled_mirrors() {
+        let mut source = RgbaImage::new(2, 1);
+        source.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+        source.put_pixel(1, 0, Rgba([0, 0, 255, 255]));
+
+        let mut canvas = RgbaImage::from_pixel(4, 2, Rgba([255, 255, 255, 255]));
+        draw_scaled(&mut canvas, &source, 4.0, 0.0, -4.0, 2.0);
+        assert_eq!(canvas.get_pixel(0, 0), &Rgba([0, 0, 255, 255]));
+        assert_eq!(canvas.get_pixel(3, 1), &Rgba([255, 0, 0, 255]));
+    }
+}
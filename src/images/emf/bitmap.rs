@@ -0,0 +1,180 @@
+/// Embedded raster records
+///
+/// Resolves the device independent bitmap carried by EMF bitmap records and
+/// where it is drawn:
+///
+/// - EMR_BITBLT (0x0000004C)
+/// - EMR_STRETCHBLT (0x0000004D)
+/// - EMR_STRETCHDIBITS (0x00000051)
+///
+/// Blits without a source bitmap only paint with the current brush and are
+/// not handled here.
+use crate::images::dib::{Dib, rop_uses_source};
+use crate::images::emf::records::*;
+use image::RgbaImage;
+use zerocopy::FromBytes;
+
+/// Size of the record type/size prefix that `EmfRecord::data` excludes
+const RECORD_PREFIX: u32 = 8;
+
+/// A bitmap drawn into a destination rectangle
+#[derive(Debug, Clone)]
+pub(crate) struct DibBlit<'a> {
+    /// Destination rectangle in logical units (x, y, width, height)
+    pub dest: (i32, i32, i32, i32),
+    /// Source rectangle in bitmap pixels (x, y, width, height)
+    pub src: (i32, i32, i32, i32),
+    /// Whether `src` is in DIB coordinates (bottom-left origin for
+    /// bottom-up bitmaps) rather than having a top-left origin
+    dib_origin: bool,
+    /// `BITMAPINFO` header and color table
+    info: &'a [u8],
+    /// Pixel bits
+    bits: &'a [u8],
+}
+
+impl<'a> DibBlit<'a> {
+    /// Parse a bitmap record from its type and data (excluding type/size),
+    /// returning `None` for other records and for blits that do not copy
+    /// a bitmap.
+    pub fn parse(record_type: u32, data: &'a [u8]) -> Option<Self> {
+        match EmrType::from_u32(record_type)? {
+            EmrType::StretchDIBits => {
+                let (hdr, _) = EmrStretchDiBitsHeader::read_from_prefix(data).ok()?;
+                if hdr.usage_src == DibUsage::PalIndices as u32 {
+                    return None;
+                }
+                Self::new(
+                    data,
+                    (hdr.x_dest, hdr.y_dest, hdr.cx_dest, hdr.cy_dest),
+                    (hdr.x_src, hdr.y_src, hdr.cx_src, hdr.cy_src),
+                    hdr.rop,
+                    true,
+                    (hdr.off_bmi_src, hdr.cb_bmi_src),
+                    (hdr.off_bits_src, hdr.cb_bits_src),
+                )
+            },
+            EmrType::BitBlt => {
+                let (hdr, _) = EmrBitBltHeader::read_from_prefix(data).ok()?;
+                Self::new(
+                    data,
+                    (hdr.x_dest, hdr.y_dest, hdr.cx_dest, hdr.cy_dest),
+                    (hdr.x_src, hdr.y_src, hdr.cx_dest, hdr.cy_dest),
+                    hdr.rop,
+                    false,
+                    (hdr.off_bmi_src, hdr.cb_bmi_src),
+                    (hdr.off_bits_src, hdr.cb_bits_src),
+                )
+            },
+            EmrType::StretchBlt => {
+                let (hdr, _) = EmrStretchBltHeader::read_from_prefix(data).ok()?;
+                Self::new(
+                    data,
+                    (hdr.x_dest, hdr.y_dest, hdr.cx_dest, hdr.cy_dest),
+                    (hdr.x_src, hdr.y_src, hdr.cx_src, hdr.cy_src),
+                    hdr.rop,
+                    false,
+                    (hdr.off_bmi_src, hdr.cb_bmi_src),
+                    (hdr.off_bits_src, hdr.cb_bits_src),
+                )
+            },
+            _ => None,
+        }
+    }
+
+    fn new(
+        data: &'a [u8],
+        dest: (i32, i32, i32, i32),
+        src: (i32, i32, i32, i32),
+        rop: u32,
+        dib_origin: bool,
+        bmi: (u32, u32),
+        bits: (u32, u32),
+    ) -> Option<Self> {
+        if !rop_uses_source(rop) {
+            return None;
+        }
+        let slice = |(offset, size): (u32, u32)| {
+            if size == 0 {
+                return None;
+            }
+            let start = offset.checked_sub(RECORD_PREFIX)? as usize;
+            data.get(start..start.checked_add(size as usize)?)
+        };
+
+        Some(Self {
+            dest,
+            src,
+            dib_origin,
+            info: slice(bmi)?,
+            bits: slice(bits)?,
+        })
+    }
+
+    /// Decode the bitmap and cut out the source rectangle
+    pub fn image(&self) -> Option<RgbaImage> {
+        let dib = Dib::parse_parts(self.info, self.bits).ok()?;
+        let (x, y, w, h) = self.src;
+        if w <= 0 || h <= 0 {
+            return Some(dib.into_image());
+        }
+        if self.dib_origin {
+            dib.dib_region(x, y, w, h)
+        } else {
+            dib.region(x, y, w, h)
+        }
+    }
+
+    /// Destination corners `((left, top), (right, bottom))` in logical
+    /// units; mirrored blits have `right < left` or `bottom < top`.
+    pub fn dest_corners(&self) -> ((f64, f64), (f64, f64)) {
+        let (x, y, w, h) = self.dest;
+        let (x, y) = (x as f64, y as f64);
+        ((x, y), (x + w as f64, y + h as f64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::images::emf::{EmfParser, convert_emf_to_svg};
+    use std::path::PathBuf;
+
+    fn fixture() -> Vec<u8> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/images/emf/dib-8bit-24bit.emf");
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn test_parse_bitmap_records() {
+        let parser = EmfParser::new(&fixture()).unwrap();
+        let blits: Vec<_> = parser
+            .records
+            .iter()
+            .filter_map(|r| DibBlit::parse(r.record_type, &r.data))
+            .collect();
+        assert_eq!(blits.len(), 3);
+
+        // EMR_STRETCHDIBITS with an RLE8-compressed 8-bit DIB
+        assert_eq!(blits[0].dest, (0, 0, 100, 100));
+        let image = blits[0].image().unwrap();
+        assert_eq!(image.get_pixel(0, 0).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(3, 0).0, [255, 255, 0, 255]);
+
+        // EMR_STRETCHBLT and EMR_BITBLT with a bottom-up 24-bit DIB
+        assert_eq!(blits[1].dest_corners(), ((100.0, 0.0), (200.0, 100.0)));
+        assert_eq!(blits[2].src, (0, 0, 4, 4));
+        let image = blits[2].image().unwrap();
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(0, 3).0, [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_svg_embeds_bitmaps() {
+        let svg = convert_emf_to_svg(&fixture()).unwrap();
+        assert_eq!(svg.matches("<image ").count(), 3);
+        assert!(svg.contains("<image x=\"100\" y=\"0\" width=\"100\" height=\"100\""));
+        assert!(svg.contains("href=\"data:image/png;base64,"));
+    }
+}
@@ -4,12 +4,15 @@
 //
 // Note: Full EMF rendering would require implementing a complete GDI rendering engine,
 // which is extremely complex. This implementation provides:
-// 1. Extraction of embedded bitmaps from EMF records
+// 1. Rendering of embedded bitmaps from EMF records
 // 2. Placeholder generation with proper dimensions
 // 3. A foundation for future full rendering support
 
+use super::emfplus::{self, Matrix};
 use super::parser::EmfParser;
+use super::svg::EmfSvgConverter;
 use crate::common::error::{Error, Result};
+use crate::images::dib::draw_scaled;
 use image::{DynamicImage, ImageBuffer, ImageFormat, Rgba, RgbaImage};
 use std::io::Cursor;
 
@@ -22,6 +25,8 @@ pub struct EmfToRasterOptions {
     pub height: Option<u32>,
     /// Background color for rendering
     pub background_color: Rgba<u8>,
+    /// Render the GDI records even when the file carries an EMF+ stream
+    pub force_gdi: bool,
 }
 
 impl Default for EmfToRasterOptions {
@@ -30,6 +35,7 @@ impl Default for EmfToRasterOptions {
             width: None,
             height: None,
             background_color: Rgba([255, 255, 255, 255]),
+            force_gdi: false,
         }
     }
 }
@@ -79,81 +85,58 @@ impl EmfConverter {
         }
     }
 
-    /// Try to extract embedded bitmap from EMF records
+    /// Render the embedded bitmaps of the EMF onto a canvas
     ///
     /// EMF files can contain embedded bitmaps via various record types:
     /// - EMR_STRETCHDIBITS (0x00000051)
-    /// - EMR_BITBLT (0x00000040)
-    /// - EMR_STRETCHBLT (0x00000041)
-    fn extract_embedded_bitmap(&self) -> Option<DynamicImage> {
-        // Scan through records looking for bitmap data
-        for record in &self.parser.records {
-            match record.record_type {
-                0x00000051 => {
-                    // EMR_STRETCHDIBITS - contains DIB data
-                    if let Some(img) = self.parse_dib_from_record(&record.data) {
-                        return Some(img);
-                    }
-                },
-                0x00000040 | 0x00000041 => {
-                    // EMR_BITBLT or EMR_STRETCHBLT
-                    if let Some(img) = self.parse_bitmap_from_bitblt(&record.data) {
-                        return Some(img);
-                    }
-                },
-                _ => {},
-            }
-        }
-        None
-    }
-
-    /// Parse DIB (Device Independent Bitmap) data from record
-    fn parse_dib_from_record(&self, data: &[u8]) -> Option<DynamicImage> {
-        // DIB data structure is complex; for now, try to detect standard formats
-        // that image crate can handle directly
-        if data.len() < 40 {
+    /// - EMR_BITBLT (0x0000004C)
+    /// - EMR_STRETCHBLT (0x0000004D)
+    ///
+    /// Bitmaps are placed as in the SVG output (through the window/viewport
+    /// and world transforms), then scaled from the header bounds to the
+    /// output size. Returns `None` if the EMF contains no decodable bitmap.
+    fn render_bitmaps(&self, width: u32, height: u32) -> Option<RgbaImage> {
+        let blits = EmfSvgConverter::new(&self.parser).placed_bitmaps();
+        if blits.is_empty() {
             return None;
         }
 
-        // Try to load as BMP (DIB is essentially BMP without file header)
-        // We need to construct a proper BMP file header
-        if let Ok(img) = self.construct_bmp_from_dib(data) {
-            return Some(img);
+        let (bounds_left, bounds_top, _, _) = self.parser.header.bounds;
+        let scale_x = width as f64 / self.parser.width().max(1) as f64;
+        let scale_y = height as f64 / self.parser.height().max(1) as f64;
+        let map = |(x, y): (f64, f64)| {
+            (
+                (x - bounds_left as f64) * scale_x,
+                (y - bounds_top as f64) * scale_y,
+            )
+        };
+
+        let mut canvas = ImageBuffer::from_pixel(width, height, self.options.background_color);
+        for (from, to, image) in &blits {
+            let (x1, y1) = map(*from);
+            let (x2, y2) = map(*to);
+            draw_scaled(&mut canvas, image, x1, y1, x2 - x1, y2 - y1);
         }
-
-        None
+        Some(canvas)
     }
 
-    /// Construct a BMP image from DIB data
-    fn construct_bmp_from_dib(&self, dib_data: &[u8]) -> Result<DynamicImage> {
-        // BMP file header is 14 bytes
-        // BITMAPFILEHEADER structure
-        let file_size = 14u32 + dib_data.len() as u32;
-        let pixel_data_offset = 14u32 + 40u32; // header + BITMAPINFOHEADER
-
-        let mut bmp_data = Vec::with_capacity(file_size as usize);
-
-        // BMP file header
-        bmp_data.extend_from_slice(b"BM"); // Signature
-        bmp_data.extend_from_slice(&file_size.to_le_bytes()); // File size
-        bmp_data.extend_from_slice(&[0u8; 4]); // Reserved
-        bmp_data.extend_from_slice(&pixel_data_offset.to_le_bytes()); // Pixel data offset
-
-        // Append DIB data
-        bmp_data.extend_from_slice(dib_data);
-
-        // Try to load the constructed BMP
-        match image::load_from_memory(&bmp_data) {
-            Ok(img) => Ok(img),
-            Err(_) => Err(Error::ParseError("Failed to load DIB as BMP".into())),
+    /// Render the EMF+ stream of the EMF, if it has one that draws anything
+    fn render_emf_plus(&self, width: u32, height: u32) -> Option<RgbaImage> {
+        if self.options.force_gdi {
+            return None;
         }
-    }
-
-    /// Parse bitmap from BITBLT record
-    fn parse_bitmap_from_bitblt(&self, _data: &[u8]) -> Option<DynamicImage> {
-        // BITBLT records are complex and may not always contain embedded bitmap data
-        // This is a placeholder for future implementation
-        None
+        let ops = emfplus::draw_ops(&self.parser)?;
+
+        let (bounds_left, bounds_top, _, _) = self.parser.header.bounds;
+        let to_pixels =
+            Matrix::translate(-bounds_left as f64, -bounds_top as f64).then(&Matrix::scale(
+                width as f64 / self.parser.width().max(1) as f64,
+                height as f64 / self.parser.height().max(1) as f64,
+            ));
+
+        let mut canvas = ImageBuffer::from_pixel(width, height, self.options.background_color);
+        emfplus::raster::render(&mut canvas, &ops, &to_pixels);
+        Some(canvas)
     }
 
     /// Create a placeholder image with EMF metadata
@@ -192,25 +175,22 @@ impl EmfConverter {
 
     /// Convert EMF to a raster image
     ///
-    /// This first attempts to extract any embedded bitmaps from the EMF.
-    /// If no bitmaps are found, it creates a placeholder image.
+    /// Files with an EMF+ stream are rendered from their EMF+ records
+    /// (shapes and images; text is not rasterized) unless
+    /// [`EmfToRasterOptions::force_gdi`] is set. Otherwise this composites
+    /// the embedded bitmaps of the EMF onto the background, or creates a
+    /// placeholder image if no bitmaps are found.
     ///
     /// TODO: Implement full EMF rendering engine for complete vector-to-raster conversion
     pub fn convert_to_image(&self) -> Result<DynamicImage> {
         let (target_width, target_height) = self.calculate_dimensions();
 
-        // Try to extract embedded bitmap first
-        if let Some(embedded) = self.extract_embedded_bitmap() {
-            // Resize if necessary
-            if embedded.width() != target_width || embedded.height() != target_height {
-                return Ok(DynamicImage::ImageRgba8(image::imageops::resize(
-                    &embedded,
-                    target_width,
-                    target_height,
-                    image::imageops::FilterType::Lanczos3,
-                )));
-            }
-            return Ok(embedded);
+        if let Some(canvas) = self.render_emf_plus(target_width, target_height) {
+            return Ok(DynamicImage::ImageRgba8(canvas));
+        }
+
+        if let Some(canvas) = self.render_bitmaps(target_width, target_height) {
+            return Ok(DynamicImage::ImageRgba8(canvas));
         }
 
         // No embedded bitmap found - create placeholder
@@ -255,9 +235,26 @@ impl EmfConverter {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
     #[test]
-    fn test_dimension_calculation() {
-        // This would require creating a valid EMF parser
-        // Placeholder for future tests
+    fn test_render_embedded_bitmaps() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/images/emf/dib-8bit-24bit.emf");
+        let parser = EmfParser::new(&std::fs::read(path).unwrap()).unwrap();
+        let converter = EmfConverter::new(parser, EmfToRasterOptions::default());
+
+        let image = converter.convert_to_image().unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (200, 100));
+        // EMR_STRETCHDIBITS with an RLE8 DIB: green and yellow halves
+        assert_eq!(image.get_pixel(25, 50).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(75, 50).0, [255, 255, 0, 255]);
+        // EMR_STRETCHBLT with a 24-bit DIB: red over blue
+        assert_eq!(image.get_pixel(150, 25).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(150, 75).0, [0, 0, 255, 255]);
+        // EMR_BITBLT copies the 24-bit DIB 1:1 into the bottom-right corner
+        assert_eq!(image.get_pixel(197, 97).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(197, 99).0, [0, 0, 255, 255]);
     }
 }
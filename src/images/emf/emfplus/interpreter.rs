@@ -0,0 +1,884 @@
+/// EMF+ playback
+///
+/// Replays EMF+ records against a graphics state (object table, world and
+/// page transforms, save stack) and produces drawing operations in device
+/// coordinates, ready for the SVG and raster backends.
+use super::objects::{
+    Argb, Brush, EmfPlusObject, Font, Path, Pen, point_type, read_points, read_rect, read_utf16,
+    unit_to_pixels,
+};
+use super::records::{EmfPlusRecord, flags, read_f32, read_u32, record_type};
+use image::RgbaImage;
+use std::f64::consts::PI;
+use std::sync::Arc;
+
+/// Number of entries in the EMF+ object table
+const OBJECT_TABLE_SIZE: usize = 64;
+
+/// Unit of the page transform when none is set (display)
+const UNIT_DISPLAY: u32 = 1;
+
+/// Bézier control point distance for quarter ellipse arcs
+const KAPPA: f64 = 0.552_284_749_830_793_4;
+
+/// Affine transform `(m11, m12, m21, m22, dx, dy)` mapping
+/// `(x, y)` to `(m11·x + m21·y + dx, m12·x + m22·y + dy)`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix(pub [f64; 6]);
+
+impl Matrix {
+    pub const IDENTITY: Matrix = Matrix([1.0, 0.0, 0.0, 1.0, 0.0, 0.0]);
+
+    pub fn translate(dx: f64, dy: f64) -> Self {
+        Matrix([1.0, 0.0, 0.0, 1.0, dx, dy])
+    }
+
+    pub fn scale(sx: f64, sy: f64) -> Self {
+        Matrix([sx, 0.0, 0.0, sy, 0.0, 0.0])
+    }
+
+    /// Clockwise rotation by `degrees` (y axis pointing down)
+    pub fn rotate(degrees: f64) -> Self {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        Matrix([cos, sin, -sin, cos, 0.0, 0.0])
+    }
+
+    /// Transform that applies `self`, then `next`
+    pub fn then(&self, next: &Matrix) -> Matrix {
+        let [a, b, c, d, e, f] = self.0;
+        let [na, nb, nc, nd, ne, nf] = next.0;
+        Matrix([
+            a * na + b * nc,
+            a * nb + b * nd,
+            c * na + d * nc,
+            c * nb + d * nd,
+            e * na + f * nc + ne,
+            e * nb + f * nd + nf,
+        ])
+    }
+
+    #[inline]
+    pub fn apply(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        let [a, b, c, d, e, f] = self.0;
+        (a * x + c * y + e, b * x + d * y + f)
+    }
+
+    /// Inverse transform, or `None` if the matrix is singular
+    pub fn invert(&self) -> Option<Matrix> {
+        let [a, b, c, d, e, f] = self.0;
+        let det = a * d - b * c;
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        Some(Matrix([
+            d / det,
+            -b / det,
+            -c / det,
+            a / det,
+            (c * f - d * e) / det,
+            (b * e - a * f) / det,
+        ]))
+    }
+
+    /// Mean length scale factor
+    pub fn scale_factor(&self) -> f64 {
+        let [a, b, c, d, _, _] = self.0;
+        (a * d - b * c).abs().sqrt()
+    }
+
+    fn read(data: &[u8], offset: usize) -> Option<Matrix> {
+        let mut m = [0.0; 6];
+        for (i, v) in m.iter_mut().enumerate() {
+            *v = read_f32(data, offset + i * 4)? as f64;
+        }
+        Some(Matrix(m))
+    }
+}
+
+/// Path segment in device coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Segment {
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    /// Cubic Bézier: two control points, then the end point
+    CubicTo((f64, f64), (f64, f64), (f64, f64)),
+    Close,
+}
+
+/// How a shape is painted
+#[derive(Debug, Clone, PartialEq)]
+pub enum Paint {
+    Solid(Argb),
+    /// Linear gradient between two device points
+    Linear {
+        from: (f64, f64),
+        to: (f64, f64),
+        start: Argb,
+        end: Argb,
+    },
+}
+
+impl Paint {
+    /// Whether painting has no visible effect
+    pub fn is_transparent(&self) -> bool {
+        match self {
+            Paint::Solid(c) => c.alpha() == 0,
+            Paint::Linear { start, end, .. } => start.alpha() == 0 && end.alpha() == 0,
+        }
+    }
+}
+
+/// Drawing operation in device coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub enum DrawOp {
+    /// Fill the whole drawing surface
+    Clear(Argb),
+    /// Fill a path using the alternate (even-odd) rule
+    Fill { path: Vec<Segment>, paint: Paint },
+    /// Stroke a path
+    Stroke {
+        path: Vec<Segment>,
+        paint: Paint,
+        width: f64,
+        /// Dash and gap lengths in device units
+        dash: Option<Vec<f64>>,
+    },
+    /// Draw an image; `transform` maps image pixels to device coordinates
+    Image {
+        image: Arc<RgbaImage>,
+        transform: Matrix,
+    },
+    /// Draw text with its baseline starting at `origin`
+    Text {
+        text: String,
+        origin: (f64, f64),
+        /// Font size in device units
+        size: f64,
+        /// Clockwise rotation in degrees
+        rotation: f64,
+        font: Font,
+        color: Argb,
+    },
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GraphicsState {
+    world: Matrix,
+    page_unit: u32,
+    page_scale: f64,
+}
+
+impl Default for GraphicsState {
+    fn default() -> Self {
+        Self {
+            world: Matrix::IDENTITY,
+            page_unit: UNIT_DISPLAY,
+            page_scale: 1.0,
+        }
+    }
+}
+
+/// Object spread over several records
+struct PartialObject {
+    id: u8,
+    kind: u8,
+    total: usize,
+    data: Vec<u8>,
+}
+
+/// Replays EMF+ records into [`DrawOp`]s
+pub struct EmfPlusInterpreter {
+    objects: Vec<Option<EmfPlusObject>>,
+    state: GraphicsState,
+    stack: Vec<(u32, GraphicsState)>,
+    dpi: (f64, f64),
+    partial: Option<PartialObject>,
+    ops: Vec<DrawOp>,
+}
+
+impl Default for EmfPlusInterpreter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EmfPlusInterpreter {
+    pub fn new() -> Self {
+        Self {
+            objects: vec![None; OBJECT_TABLE_SIZE],
+            state: GraphicsState::default(),
+            stack: Vec::new(),
+            dpi: (96.0, 96.0),
+            partial: None,
+            ops: Vec::new(),
+        }
+    }
+
+    /// Replay `records` and return the resulting drawing operations
+    pub fn run(records: &[EmfPlusRecord]) -> Vec<DrawOp> {
+        let mut interpreter = Self::new();
+        for record in records {
+            if record.record_type == record_type::END_OF_FILE {
+                break;
+            }
+            // Malformed records are skipped
+            let _ = interpreter.play(record);
+        }
+        interpreter.ops
+    }
+
+    /// Transform from world coordinates to device coordinates
+    fn device_transform(&self) -> Matrix {
+        let GraphicsState {
+            world,
+            page_unit,
+            page_scale,
+        } = self.state;
+        let sx = unit_to_pixels(page_unit, self.dpi.0) * page_scale;
+        let sy = unit_to_pixels(page_unit, self.dpi.1) * page_scale;
+        world.then(&Matrix::scale(sx, sy))
+    }
+
+    fn play(&mut self, rec: &EmfPlusRecord) -> Option<()> {
+        let d = &rec.data[..];
+        let compressed = rec.has_flag(flags::COMPRESSED);
+
+        match rec.record_type {
+            record_type::HEADER => {
+                let (x, y) = (read_u32(d, 8)?, read_u32(d, 12)?);
+                if x > 0 && y > 0 {
+                    self.dpi = (x as f64, y as f64);
+                }
+            },
+            record_type::OBJECT => self.define_object(rec)?,
+            record_type::CLEAR => self.ops.push(DrawOp::Clear(Argb(read_u32(d, 0)?))),
+
+            record_type::FILL_RECTS => {
+                let paint = self.brush_paint(rec, read_u32(d, 0)?)?;
+                let count = read_u32(d, 4)? as usize;
+                let size = if compressed { 8 } else { 16 };
+                for i in 0..count.min(d.len() / size) {
+                    let rect = read_rect(d, 8 + i * size, compressed)?;
+                    self.fill(rect_figure(rect), paint.clone());
+                }
+            },
+            record_type::DRAW_RECTS => {
+                let count = read_u32(d, 0)? as usize;
+                let size = if compressed { 8 } else { 16 };
+                for i in 0..count.min(d.len() / size) {
+                    let rect = read_rect(d, 4 + i * size, compressed)?;
+                    self.stroke(rec.object_id(), rect_figure(rect))?;
+                }
+            },
+            record_type::FILL_POLYGON => {
+                let paint = self.brush_paint(rec, read_u32(d, 0)?)?;
+                let points = self.read_points(rec, 4)?;
+                self.fill(polyline_figure(&points, true), paint);
+            },
+            record_type::DRAW_LINES => {
+                let points = self.read_points(rec, 0)?;
+                let closed = rec.has_flag(flags::CLOSED);
+                self.stroke(rec.object_id(), polyline_figure(&points, closed))?;
+            },
+            record_type::DRAW_BEZIERS => {
+                let points = self.read_points(rec, 0)?;
+                self.stroke(rec.object_id(), bezier_figure(&points))?;
+            },
+            record_type::FILL_ELLIPSE => {
+                let paint = self.brush_paint(rec, read_u32(d, 0)?)?;
+                let rect = read_rect(d, 4, compressed)?;
+                self.fill(ellipse_figure(rect), paint);
+            },
+            record_type::DRAW_ELLIPSE => {
+                let rect = read_rect(d, 0, compressed)?;
+                self.stroke(rec.object_id(), ellipse_figure(rect))?;
+            },
+            record_type::FILL_PIE => {
+                let paint = self.brush_paint(rec, read_u32(d, 0)?)?;
+                let (start, sweep) = (read_f32(d, 4)?, read_f32(d, 8)?);
+                let rect = read_rect(d, 12, compressed)?;
+                self.fill(arc_figure(rect, start, sweep, true), paint);
+            },
+            record_type::DRAW_PIE | record_type::DRAW_ARC => {
+                let (start, sweep) = (read_f32(d, 0)?, read_f32(d, 4)?);
+                let rect = read_rect(d, 8, compressed)?;
+                let pie = rec.record_type == record_type::DRAW_PIE;
+                self.stroke(rec.object_id(), arc_figure(rect, start, sweep, pie))?;
+            },
+            record_type::FILL_PATH => {
+                let paint = self.brush_paint(rec, read_u32(d, 0)?)?;
+                let figure = self.path_figure(rec.object_id())?;
+                self.fill(figure, paint);
+            },
+            record_type::DRAW_PATH => {
+                let figure = self.path_figure(rec.object_id())?;
+                self.stroke(read_u32(d, 0)? as u8, figure)?;
+            },
+            record_type::DRAW_IMAGE => {
+                let src = read_rect(d, 8, false)?;
+                let (x, y, w, h) = read_rect(d, 24, compressed)?;
+                self.draw_image(rec.object_id(), src, [(x, y), (x + w, y), (x, y + h)])?;
+            },
+            record_type::DRAW_IMAGE_POINTS => {
+                if rec.has_flag(flags::RELATIVE) || read_u32(d, 24)? != 3 {
+                    return None;
+                }
+                let src = read_rect(d, 8, false)?;
+                let p = read_points(d, 28, 3, compressed)?;
+                self.draw_image(rec.object_id(), src, [p[0], p[1], p[2]])?;
+            },
+            record_type::DRAW_STRING => {
+                let color = self.brush_paint(rec, read_u32(d, 0)?)?;
+                let length = read_u32(d, 8)? as usize;
+                let (x, y, _, _) = read_rect(d, 12, false)?;
+                let text = read_utf16(d, 28, length)?;
+                self.text(rec.object_id(), text, (x, y), color, false)?;
+            },
+            record_type::DRAW_DRIVER_STRING => {
+                const CMAP_LOOKUP: u32 = 1;
+                let color = self.brush_paint(rec, read_u32(d, 0)?)?;
+                let count = read_u32(d, 12)? as usize;
+                // Glyph indices cannot be mapped back to characters
+                if read_u32(d, 4)? & CMAP_LOOKUP == 0 || count == 0 {
+                    return None;
+                }
+                let text = read_utf16(d, 16, count)?;
+                let origin = read_points(d, 16 + count * 2, 1, false)?[0];
+                self.text(rec.object_id(), text, origin, color, true)?;
+            },
+
+            record_type::SAVE | record_type::BEGIN_CONTAINER_NO_PARAMS => {
+                self.stack.push((read_u32(d, 0)?, self.state));
+            },
+            record_type::BEGIN_CONTAINER => {
+                self.stack.push((read_u32(d, 32)?, self.state));
+                let (dx, dy, dw, dh) = read_rect(d, 0, false)?;
+                let (sx, sy, sw, sh) = read_rect(d, 16, false)?;
+                if sw != 0.0 && sh != 0.0 {
+                    let (scale_x, scale_y) = ((dw / sw) as f64, (dh / sh) as f64);
+                    let container = Matrix::translate(-sx as f64, -sy as f64)
+                        .then(&Matrix::scale(scale_x, scale_y))
+                        .then(&Matrix::translate(dx as f64, dy as f64));
+                    self.state.world = container.then(&self.state.world);
+                }
+            },
+            record_type::RESTORE | record_type::END_CONTAINER => {
+                let index = read_u32(d, 0)?;
+                if let Some(pos) = self.stack.iter().rposition(|(i, _)| *i == index) {
+                    self.state = self.stack[pos].1;
+                    self.stack.truncate(pos);
+                }
+            },
+
+            record_type::SET_WORLD_TRANSFORM => self.state.world = Matrix::read(d, 0)?,
+            record_type::RESET_WORLD_TRANSFORM => self.state.world = Matrix::IDENTITY,
+            record_type::MULTIPLY_WORLD_TRANSFORM => self.combine(rec, Matrix::read(d, 0)?),
+            record_type::TRANSLATE_WORLD_TRANSFORM => {
+                let m = Matrix::translate(read_f32(d, 0)? as f64, read_f32(d, 4)? as f64);
+                self.combine(rec, m);
+            },
+            record_type::SCALE_WORLD_TRANSFORM => {
+                let m = Matrix::scale(read_f32(d, 0)? as f64, read_f32(d, 4)? as f64);
+                self.combine(rec, m);
+            },
+            record_type::ROTATE_WORLD_TRANSFORM => {
+                self.combine(rec, Matrix::rotate(read_f32(d, 0)? as f64));
+            },
+            record_type::SET_PAGE_TRANSFORM => {
+                self.state.page_unit = rec.object_id() as u32;
+                self.state.page_scale = read_f32(d, 0)? as f64;
+            },
+            _ => {},
+        }
+        Some(())
+    }
+
+    /// Apply a world transform record, honoring its append flag
+    fn combine(&mut self, rec: &EmfPlusRecord, m: Matrix) {
+        self.state.world = if rec.has_flag(flags::APPEND) {
+            self.state.world.then(&m)
+        } else {
+            m.then(&self.state.world)
+        };
+    }
+
+    fn define_object(&mut self, rec: &EmfPlusRecord) -> Option<()> {
+        let id = rec.object_id();
+        let kind = ((rec.flags >> 8) & 0x7F) as u8;
+
+        let data = if rec.has_flag(flags::CONTINUE_OBJECT) {
+            // Each piece starts with the total size of the object
+            let total = read_u32(&rec.data, 0)? as usize;
+            let piece = rec.data.get(4..)?;
+            let mut partial = match self.partial.take() {
+                Some(p) if p.id == id && p.kind == kind => p,
+                _ => PartialObject {
+                    id,
+                    kind,
+                    total,
+                    data: Vec::new(),
+                },
+            };
+            partial.data.extend_from_slice(piece);
+            if partial.data.len() < partial.total {
+                self.partial = Some(partial);
+                return Some(());
+            }
+            partial.data
+        } else {
+            match self.partial.take() {
+                // The last piece of a continued object has the flag cleared
+                Some(mut p) if p.id == id && p.kind == kind => {
+                    p.data.extend_from_slice(&rec.data);
+                    p.data
+                },
+                _ => rec.data.clone(),
+            }
+        };
+
+        *self.objects.get_mut(id as usize)? = EmfPlusObject::parse(kind, &data);
+        Some(())
+    }
+
+    fn object(&self, id: u8) -> Option<&EmfPlusObject> {
+        self.objects.get(id as usize)?.as_ref()
+    }
+
+    /// Paint of a brush given by id, or by ARGB color with the solid flag
+    fn brush_paint(&self, rec: &EmfPlusRecord, value: u32) -> Option<Paint> {
+        if rec.has_flag(flags::SOLID_COLOR) {
+            return Some(Paint::Solid(Argb(value)));
+        }
+        match self.object(value as u8)? {
+            EmfPlusObject::Brush(brush) => Some(self.paint(brush)),
+            _ => None,
+        }
+    }
+
+    fn paint(&self, brush: &Brush) -> Paint {
+        match *brush {
+            Brush::LinearGradient {
+                rect: (x, y, w, h),
+                start,
+                end,
+            } => {
+                let m = self.device_transform();
+                let mid = (y + h / 2.0) as f64;
+                Paint::Linear {
+                    from: m.apply((x as f64, mid)),
+                    to: m.apply(((x + w) as f64, mid)),
+                    start,
+                    end,
+                }
+            },
+            ref other => Paint::Solid(other.color()),
+        }
+    }
+
+    fn read_points(&self, rec: &EmfPlusRecord, offset: usize) -> Option<Vec<(f32, f32)>> {
+        if rec.has_flag(flags::RELATIVE) {
+            return None;
+        }
+        let count = read_u32(&rec.data, offset)? as usize;
+        read_points(
+            &rec.data,
+            offset + 4,
+            count,
+            rec.has_flag(flags::COMPRESSED),
+        )
+    }
+
+    fn path_figure(&self, id: u8) -> Option<Vec<Figure>> {
+        match self.object(id)? {
+            EmfPlusObject::Path(path) => Some(path_figure(path)),
+            _ => None,
+        }
+    }
+
+    fn fill(&mut self, figure: Vec<Figure>, paint: Paint) {
+        if paint.is_transparent() {
+            return;
+        }
+        let path = self.to_device(&figure);
+        if !path.is_empty() {
+            self.ops.push(DrawOp::Fill { path, paint });
+        }
+    }
+
+    fn stroke(&mut self, pen_id: u8, figure: Vec<Figure>) -> Option<()> {
+        let EmfPlusObject::Pen(pen) = self.object(pen_id)? else {
+            return None;
+        };
+        let Pen {
+            width, unit, dash, ..
+        } = pen;
+        let paint = self.paint(&pen.brush);
+        if paint.is_transparent() {
+            return Some(());
+        }
+
+        // World-unit widths scale with the transform; a zero width is one
+        // device pixel wide.
+        let width = if *unit == 0 {
+            *width as f64 * self.device_transform().scale_factor()
+        } else {
+            *width as f64 * unit_to_pixels(*unit, self.dpi.0)
+        };
+        let width = if width > 0.0 { width } else { 1.0 };
+        let dash = dash
+            .pattern()
+            .map(|p| p.iter().map(|d| *d as f64 * width).collect());
+
+        let path = self.to_device(&figure);
+        if !path.is_empty() {
+            self.ops.push(DrawOp::Stroke {
+                path,
+                paint,
+                width,
+                dash,
+            });
+        }
+        Some(())
+    }
+
+    fn draw_image(
+        &mut self,
+        id: u8,
+        (sx, sy, sw, sh): (f32, f32, f32, f32),
+        [ul, ur, ll]: [(f32, f32); 3],
+    ) -> Option<()> {
+        let EmfPlusObject::Image(image) = self.object(id)? else {
+            return None;
+        };
+
+        // Cut out the source rectangle (in pixels) unless it is the whole image
+        let (x, y) = (sx.max(0.0) as u32, sy.max(0.0) as u32);
+        let w = (sw.round().max(0.0) as u32).min(image.width().saturating_sub(x));
+        let h = (sh.round().max(0.0) as u32).min(image.height().saturating_sub(y));
+        if w == 0 || h == 0 {
+            return None;
+        }
+        let image = if (x, y, w, h) == (0, 0, image.width(), image.height()) {
+            Arc::clone(image)
+        } else {
+            Arc::new(image::imageops::crop_imm(image.as_ref(), x, y, w, h).to_image())
+        };
+
+        let (w, h) = (w as f64, h as f64);
+        let placement = Matrix([
+            (ur.0 - ul.0) as f64 / w,
+            (ur.1 - ul.1) as f64 / w,
+            (ll.0 - ul.0) as f64 / h,
+            (ll.1 - ul.1) as f64 / h,
+            ul.0 as f64,
+            ul.1 as f64,
+        ]);
+        self.ops.push(DrawOp::Image {
+            image,
+            transform: placement.then(&self.device_transform()),
+        });
+        Some(())
+    }
+
+    /// Queue text; `baseline` is set when `origin` is on the baseline rather
+    /// than the top of the layout rectangle.
+    fn text(
+        &mut self,
+        font_id: u8,
+        text: String,
+        origin: (f32, f32),
+        paint: Paint,
+        baseline: bool,
+    ) -> Option<()> {
+        let EmfPlusObject::Font(font) = self.object(font_id)? else {
+            return None;
+        };
+        let text = text.trim_end_matches('\0').to_string();
+        if text.is_empty() {
+            return Some(());
+        }
+
+        let m = self.device_transform();
+        let em = if font.unit == 0 {
+            font.em_size as f64
+        } else {
+            font.em_size as f64 * unit_to_pixels(font.unit, self.dpi.1) / m.scale_factor().max(1e-9)
+        };
+        // Approximate the ascent of a layout rectangle's first line
+        let (x, y) = (origin.0 as f64, origin.1 as f64);
+        let origin = if baseline { (x, y) } else { (x, y + em * 0.9) };
+
+        let [a, b, ..] = m.0;
+        self.ops.push(DrawOp::Text {
+            text,
+            origin: m.apply(origin),
+            size: em * m.scale_factor(),
+            rotation: b.atan2(a) * 180.0 / PI,
+            font: font.clone(),
+            color: match paint {
+                Paint::Solid(c) => c,
+                Paint::Linear { start, .. } => start,
+            },
+        });
+        Some(())
+    }
+
+    fn to_device(&self, figures: &[Figure]) -> Vec<Segment> {
+        let m = self.device_transform();
+        let mut path = Vec::new();
+        for figure in figures {
+            if figure.points.is_empty() {
+                continue;
+            }
+            let p = |i: usize| m.apply(figure.points[i]);
+            let (x, y) = p(0);
+            path.push(Segment::MoveTo(x, y));
+            let mut i = 1;
+            for kind in &figure.kinds {
+                match kind {
+                    FigureSegment::Line => {
+                        let (x, y) = p(i);
+                        path.push(Segment::LineTo(x, y));
+                        i += 1;
+                    },
+                    FigureSegment::Cubic => {
+                        path.push(Segment::CubicTo(p(i), p(i + 1), p(i + 2)));
+                        i += 3;
+                    },
+                }
+            }
+            if figure.closed {
+                path.push(Segment::Close);
+            }
+        }
+        path
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum FigureSegment {
+    Line,
+    Cubic,
+}
+
+/// Subpath in world coordinates
+#[derive(Debug, Default)]
+struct Figure {
+    points: Vec<(f64, f64)>,
+    kinds: Vec<FigureSegment>,
+    closed: bool,
+}
+
+impl Figure {
+    fn start(p: (f64, f64)) -> Self {
+        Figure {
+            points: vec![p],
+            ..Default::default()
+        }
+    }
+
+    fn line(&mut self, p: (f64, f64)) {
+        self.points.push(p);
+        self.kinds.push(FigureSegment::Line);
+    }
+
+    fn cubic(&mut self, c1: (f64, f64), c2: (f64, f64), p: (f64, f64)) {
+        self.points.extend([c1, c2, p]);
+        self.kinds.push(FigureSegment::Cubic);
+    }
+}
+
+fn to_f64((x, y): (f32, f32)) -> (f64, f64) {
+    (x as f64, y as f64)
+}
+
+fn rect_figure((x, y, w, h): (f32, f32, f32, f32)) -> Vec<Figure> {
+    let (x, y, w, h) = (x as f64, y as f64, w as f64, h as f64);
+    let mut f = Figure::start((x, y));
+    f.line((x + w, y));
+    f.line((x + w, y + h));
+    f.line((x, y + h));
+    f.closed = true;
+    vec![f]
+}
+
+fn polyline_figure(points: &[(f32, f32)], closed: bool) -> Vec<Figure> {
+    let Some((first, rest)) = points.split_first() else {
+        return Vec::new();
+    };
+    let mut f = Figure::start(to_f64(*first));
+    for p in rest {
+        f.line(to_f64(*p));
+    }
+    f.closed = closed;
+    vec![f]
+}
+
+/// Start point followed by (control, control, end) triples
+fn bezier_figure(points: &[(f32, f32)]) -> Vec<Figure> {
+    let Some((first, rest)) = points.split_first() else {
+        return Vec::new();
+    };
+    let mut f = Figure::start(to_f64(*first));
+    for c in rest.chunks_exact(3) {
+        f.cubic(to_f64(c[0]), to_f64(c[1]), to_f64(c[2]));
+    }
+    vec![f]
+}
+
+fn ellipse_figure((x, y, w, h): (f32, f32, f32, f32)) -> Vec<Figure> {
+    let (rx, ry) = (w as f64 / 2.0, h as f64 / 2.0);
+    let (cx, cy) = (x as f64 + rx, y as f64 + ry);
+    let (kx, ky) = (rx * KAPPA, ry * KAPPA);
+
+    let mut f = Figure::start((cx + rx, cy));
+    f.cubic((cx + rx, cy + ky), (cx + kx, cy + ry), (cx, cy + ry));
+    f.cubic((cx - kx, cy + ry), (cx - rx, cy + ky), (cx - rx, cy));
+    f.cubic((cx - rx, cy - ky), (cx - kx, cy - ry), (cx, cy - ry));
+    f.cubic((cx + kx, cy - ry), (cx + rx, cy - ky), (cx + rx, cy));
+    f.closed = true;
+    vec![f]
+}
+
+/// Elliptical arc (angles in degrees, clockwise from the x axis) as line
+/// segments, closed through the center for pies
+fn arc_figure(rect: (f32, f32, f32, f32), start: f32, sweep: f32, pie: bool) -> Vec<Figure> {
+    let (x, y, w, h) = (rect.0 as f64, rect.1 as f64, rect.2 as f64, rect.3 as f64);
+    let (rx, ry) = (w / 2.0, h / 2.0);
+    let (cx, cy) = (x + rx, y + ry);
+    let sweep = (sweep as f64).clamp(-360.0, 360.0);
+    let steps = ((sweep.abs() / 5.0).ceil() as usize).max(1);
+
+    let point = |i: usize| {
+        let angle = (start as f64 + sweep * i as f64 / steps as f64).to_radians();
+        (cx + rx * angle.cos(), cy + ry * angle.sin())
+    };
+    let mut f = if pie {
+        let mut f = Figure::start((cx, cy));
+        f.line(point(0));
+        f
+    } else {
+        Figure::start(point(0))
+    };
+    for i in 1..=steps {
+        f.line(point(i));
+    }
+    f.closed = pie;
+    vec![f]
+}
+
+fn path_figure(path: &Path) -> Vec<Figure> {
+    let mut figures: Vec<Figure> = Vec::new();
+    let mut i = 0;
+    while i < path.points.len() {
+        let kind = path.types.get(i).copied().unwrap_or(point_type::LINE);
+        let p = to_f64(path.points[i]);
+        let mut last = i;
+
+        match (kind & point_type::TYPE_MASK, figures.last_mut()) {
+            (point_type::START, _) | (_, None) => figures.push(Figure::start(p)),
+            (point_type::BEZIER, Some(f)) if i + 2 < path.points.len() => {
+                f.cubic(p, to_f64(path.points[i + 1]), to_f64(path.points[i + 2]));
+                last = i + 2;
+            },
+            (_, Some(f)) => f.line(p),
+        }
+
+        let closes = path.types.get(last).copied().unwrap_or(0) & point_type::CLOSE != 0;
+        if let Some(f) = figures.last_mut() {
+            f.closed |= closes;
+        }
+        i = last + 1;
+    }
+    figures
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(record_type: u16, flags: u16, values: &[u32]) -> EmfPlusRecord {
+        EmfPlusRecord {
+            record_type,
+            flags,
+            data: values.iter().flat_map(|v| v.to_le_bytes()).collect(),
+        }
+    }
+
+    fn f(v: f32) -> u32 {
+        v.to_bits()
+    }
+
+    #[test]
+    fn test_matrix_composition() {
+        let m = Matrix::translate(10.0, 0.0).then(&Matrix::scale(2.0, 3.0));
+        assert_eq!(m.apply((1.0, 1.0)), (22.0, 3.0));
+        let inv = m.invert().unwrap();
+        assert_eq!(inv.apply((22.0, 3.0)), (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_world_and_page_transforms() {
+        let records = [
+            // Translate by (10, 20), then prepend a scale by 2
+            record(
+                record_type::TRANSLATE_WORLD_TRANSFORM,
+                0,
+                &[f(10.0), f(20.0)],
+            ),
+            record(record_type::SCALE_WORLD_TRANSFORM, 0, &[f(2.0), f(2.0)]),
+            // Page unit: pixel
+            record(record_type::SET_PAGE_TRANSFORM, 2, &[f(1.0)]),
+            record(
+                record_type::FILL_RECTS,
+                flags::SOLID_COLOR,
+                &[0xFFFF_0000, 1, f(0.0), f(0.0), f(5.0), f(5.0)],
+            ),
+        ];
+        let ops = EmfPlusInterpreter::run(&records);
+        let DrawOp::Fill { path, paint } = &ops[0] else {
+            panic!("expected a fill");
+        };
+        assert_eq!(*paint, Paint::Solid(Argb(0xFFFF_0000)));
+        assert_eq!(path[0], Segment::MoveTo(10.0, 20.0));
+        assert_eq!(path[2], Segment::LineTo(20.0, 30.0));
+    }
+
+    #[test]
+    fn test_save_restore_and_continued_objects() {
+        let brush: [u32; 3] = [0xDBC0_1002, 0, 0xFF00_FF00];
+        let bytes: Vec<u8> = brush.iter().flat_map(|v| v.to_le_bytes()).collect();
+        let piece = |flags: u16, part: &[u8]| {
+            let mut data = 12u32.to_le_bytes().to_vec();
+            data.extend_from_slice(part);
+            EmfPlusRecord {
+                record_type: record_type::OBJECT,
+                flags,
+                data,
+            }
+        };
+
+        let records = [
+            piece(0x8103, &bytes[..6]),
+            piece(0x8103, &bytes[6..]),
+            record(record_type::SAVE, 0, &[7]),
+            record(
+                record_type::TRANSLATE_WORLD_TRANSFORM,
+                0,
+                &[f(50.0), f(0.0)],
+            ),
+            record(record_type::RESTORE, 0, &[7]),
+            record(
+                record_type::FILL_RECTS,
+                0,
+                &[3, 1, f(1.0), f(2.0), f(3.0), f(4.0)],
+            ),
+        ];
+        let ops = EmfPlusInterpreter::run(&records);
+        let DrawOp::Fill { path, paint } = &ops[0] else {
+            panic!("expected a fill");
+        };
+        assert_eq!(*paint, Paint::Solid(Argb(0xFF00_FF00)));
+        assert_eq!(path[0], Segment::MoveTo(1.0, 2.0));
+    }
+}
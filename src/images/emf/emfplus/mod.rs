@@ -0,0 +1,120 @@
+/// EMF+ (GDI+) Records
+///
+/// GDI+ stores its drawing commands as EMF+ records inside EMR_COMMENT
+/// records. "Dual" files also carry an equivalent GDI rendering, but it is
+/// often a lossy approximation (no alpha, gradients or anti-aliased text
+/// placement), while "EMF+ only" files have no usable GDI records at all.
+///
+/// This module extracts the EMF+ stream, parses its objects (brushes, pens,
+/// paths, fonts and bitmap images) and replays the drawing records into
+/// [`DrawOp`]s in EMF device coordinates, which the SVG and raster backends
+/// then render.
+///
+/// Regions, clipping, cardinal curves and metafile images are not supported.
+///
+/// # References
+///
+/// - [MS-EMFPLUS]: Enhanced Metafile Format Plus Extensions
+pub mod interpreter;
+pub mod objects;
+pub(crate) mod raster;
+pub mod records;
+pub(crate) mod svg;
+
+pub use interpreter::{DrawOp, EmfPlusInterpreter, Matrix, Paint, Segment};
+pub use objects::{Argb, EmfPlusObject};
+pub use records::EmfPlusRecord;
+
+use super::parser::EmfParser;
+use records::record_type;
+
+/// Replay the EMF+ stream of an EMF
+///
+/// Returns `None` if the file has no EMF+ header or the EMF+ records draw
+/// nothing (e.g. dual files that leave all drawing to GDI).
+pub fn draw_ops(parser: &EmfParser) -> Option<Vec<DrawOp>> {
+    let records = EmfPlusRecord::extract(&parser.records);
+    if records.first()?.record_type != record_type::HEADER {
+        return None;
+    }
+    let ops = EmfPlusInterpreter::run(&records);
+    (!ops.is_empty()).then_some(ops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::images::emf::{EmfConverter, EmfSvgConverter, EmfToRasterOptions};
+    use std::path::PathBuf;
+
+    fn parser() -> EmfParser {
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/images/emf/emfplus-dual.emf");
+        EmfParser::new(&std::fs::read(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_extract_records() {
+        let records = EmfPlusRecord::extract(&parser().records);
+        let types: Vec<u16> = records.iter().map(|r| r.record_type).collect();
+        assert_eq!(
+            types,
+            [
+                record_type::HEADER,
+                record_type::OBJECT,
+                record_type::OBJECT,
+                record_type::OBJECT,
+                record_type::FILL_RECTS,
+                record_type::FILL_ELLIPSE,
+                record_type::DRAW_LINES,
+                record_type::DRAW_STRING,
+                record_type::END_OF_FILE,
+            ]
+        );
+        assert_eq!(records[3].object_id(), 2);
+    }
+
+    #[test]
+    fn test_svg_prefers_emf_plus() {
+        let parser = parser();
+        let svg = EmfSvgConverter::new(&parser).convert().unwrap();
+        assert!(svg.contains("<path d=\"M10 10L90 10L90 50L10 50Z\" fill=\"#ff0000\""));
+        assert!(svg.contains("fill=\"#00ff00\""));
+        assert!(svg.contains(
+            "<path d=\"M10 70L90 70\" fill=\"none\" stroke=\"#0000ff\" stroke-width=\"2\"/>"
+        ));
+        assert!(svg.contains("font-family=\"Arial\" font-size=\"12\""));
+        assert!(svg.contains(">Hello &amp; EMF+</text>"));
+
+        // The GDI rendering only has the fallback rectangle
+        let gdi = EmfSvgConverter::new(&parser)
+            .force_gdi(true)
+            .convert()
+            .unwrap();
+        assert!(!gdi.contains("#ff0000"));
+        assert!(!gdi.contains("Hello"));
+    }
+
+    #[test]
+    fn test_raster_prefers_emf_plus() {
+        let image = EmfConverter::new(parser(), EmfToRasterOptions::default())
+            .convert_to_image()
+            .unwrap()
+            .to_rgba8();
+        assert_eq!(image.dimensions(), (200, 100));
+        assert_eq!(image.get_pixel(50, 30).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(150, 50).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(50, 69).0, [0, 0, 255, 255]);
+        assert_eq!(image.get_pixel(5, 5).0, [255, 255, 255, 255]);
+
+        let options = EmfToRasterOptions {
+            force_gdi: true,
+            ..Default::default()
+        };
+        let image = EmfConverter::new(parser(), options)
+            .convert_to_image()
+            .unwrap()
+            .to_rgba8();
+        assert_ne!(image.get_pixel(50, 30).0, [255, 0, 0, 255]);
+    }
+}
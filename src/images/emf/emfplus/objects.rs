@@ -0,0 +1,502 @@
+/// EMF+ graphics objects
+///
+/// Objects are defined by EmfPlusObject records and stored in a 64-entry
+/// table; drawing records refer to them by id. Only the parts needed for
+/// rendering are kept.
+use super::records::{read_f32, read_i16, read_i32, read_u32};
+use image::RgbaImage;
+use std::sync::Arc;
+
+/// Object types carried in bits 8..15 of the object record flags
+pub mod object_type {
+    pub const BRUSH: u8 = 1;
+    pub const PEN: u8 = 2;
+    pub const PATH: u8 = 3;
+    pub const REGION: u8 = 4;
+    pub const IMAGE: u8 = 5;
+    pub const FONT: u8 = 6;
+    pub const STRING_FORMAT: u8 = 7;
+    pub const IMAGE_ATTRIBUTES: u8 = 8;
+    pub const CUSTOM_LINE_CAP: u8 = 9;
+}
+
+/// Color in 0xAARRGGBB form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Argb(pub u32);
+
+impl Argb {
+    #[inline]
+    pub fn alpha(self) -> u8 {
+        (self.0 >> 24) as u8
+    }
+
+    #[inline]
+    pub fn red(self) -> u8 {
+        (self.0 >> 16) as u8
+    }
+
+    #[inline]
+    pub fn green(self) -> u8 {
+        (self.0 >> 8) as u8
+    }
+
+    #[inline]
+    pub fn blue(self) -> u8 {
+        self.0 as u8
+    }
+
+    /// Components as `[r, g, b, a]`
+    #[inline]
+    pub fn to_rgba(self) -> [u8; 4] {
+        [self.red(), self.green(), self.blue(), self.alpha()]
+    }
+}
+
+/// Brush used to fill shapes and text
+#[derive(Debug, Clone, PartialEq)]
+pub enum Brush {
+    /// Solid color
+    Solid(Argb),
+    /// Hatch pattern, approximated by its foreground color
+    Hatch { style: u32, fore: Argb, back: Argb },
+    /// Linear gradient from the left edge of `rect` to its right edge
+    LinearGradient {
+        rect: (f32, f32, f32, f32),
+        start: Argb,
+        end: Argb,
+    },
+    /// Path gradient, approximated by its center color
+    PathGradient(Argb),
+    /// Texture brush, which is not decoded
+    Texture,
+}
+
+impl Brush {
+    /// Parse an EmfPlusBrush object
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let color = |offset| read_u32(data, offset).map(Argb);
+        match read_u32(data, 4)? {
+            0 => Some(Brush::Solid(color(8)?)),
+            1 => Some(Brush::Hatch {
+                style: read_u32(data, 8)?,
+                fore: color(12)?,
+                back: color(16)?,
+            }),
+            2 => Some(Brush::Texture),
+            3 => Some(Brush::PathGradient(color(16)?)),
+            4 => Some(Brush::LinearGradient {
+                rect: (
+                    read_f32(data, 16)?,
+                    read_f32(data, 20)?,
+                    read_f32(data, 24)?,
+                    read_f32(data, 28)?,
+                ),
+                start: color(32)?,
+                end: color(36)?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// A single color representing the brush
+    pub fn color(&self) -> Argb {
+        match self {
+            Brush::Solid(c) | Brush::PathGradient(c) => *c,
+            Brush::Hatch { fore, .. } => *fore,
+            Brush::LinearGradient { start, .. } => *start,
+            Brush::Texture => Argb(0xFF80_8080),
+        }
+    }
+}
+
+/// Line dash style of a pen
+#[derive(Debug, Clone, PartialEq)]
+pub enum DashStyle {
+    Solid,
+    Dash,
+    Dot,
+    DashDot,
+    DashDotDot,
+    /// Dash and gap lengths as multiples of the pen width
+    Custom(Vec<f32>),
+}
+
+impl DashStyle {
+    /// Dash and gap lengths as multiples of the pen width, or `None` for
+    /// solid lines
+    pub fn pattern(&self) -> Option<Vec<f32>> {
+        match self {
+            DashStyle::Solid => None,
+            DashStyle::Dash => Some(vec![3.0, 1.0]),
+            DashStyle::Dot => Some(vec![1.0, 1.0]),
+            DashStyle::DashDot => Some(vec![3.0, 1.0, 1.0, 1.0]),
+            DashStyle::DashDotDot => Some(vec![3.0, 1.0, 1.0, 1.0, 1.0, 1.0]),
+            DashStyle::Custom(pattern) if !pattern.is_empty() => Some(pattern.clone()),
+            DashStyle::Custom(_) => None,
+        }
+    }
+}
+
+/// Pen used to stroke lines and outlines
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pen {
+    /// Width in `unit`
+    pub width: f32,
+    /// Unit of the width (see [`unit_to_pixels`])
+    pub unit: u32,
+    pub dash: DashStyle,
+    pub brush: Brush,
+}
+
+impl Pen {
+    /// Parse an EmfPlusPen object
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let flags = read_u32(data, 8)?;
+        let unit = read_u32(data, 12)?;
+        let width = read_f32(data, 16)?;
+
+        // Optional data follows in flag order; skip what is not needed.
+        let mut offset = 20;
+        let mut line_style = 0;
+        let mut dashes = Vec::new();
+        let counted = |offset: usize, item: usize| -> Option<usize> {
+            Some(4 + read_u32(data, offset)? as usize * item)
+        };
+        for bit in 0..13 {
+            if flags & (1 << bit) == 0 {
+                continue;
+            }
+            offset += match bit {
+                0 => 24,
+                5 => {
+                    line_style = read_i32(data, offset)?;
+                    4
+                },
+                8 => {
+                    let count = read_u32(data, offset)? as usize;
+                    dashes = (0..count.min(64))
+                        .map(|i| read_f32(data, offset + 4 + i * 4))
+                        .collect::<Option<_>>()?;
+                    4 + count * 4
+                },
+                10 => counted(offset, 4)?,
+                11 | 12 => counted(offset, 1)?,
+                _ => 4,
+            };
+        }
+
+        let dash = match line_style {
+            1 => DashStyle::Dash,
+            2 => DashStyle::Dot,
+            3 => DashStyle::DashDot,
+            4 => DashStyle::DashDotDot,
+            5 => DashStyle::Custom(dashes),
+            _ => DashStyle::Solid,
+        };
+        Some(Pen {
+            width,
+            unit,
+            dash,
+            brush: Brush::parse(data.get(offset..)?)?,
+        })
+    }
+}
+
+/// Font used by string drawing records
+#[derive(Debug, Clone, PartialEq)]
+pub struct Font {
+    /// Em size in `unit`
+    pub em_size: f32,
+    /// Unit of the size (see [`unit_to_pixels`])
+    pub unit: u32,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikeout: bool,
+    pub family: String,
+}
+
+impl Font {
+    /// Parse an EmfPlusFont object
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let style = read_u32(data, 12)?;
+        let length = read_u32(data, 20)? as usize;
+        Some(Font {
+            em_size: read_f32(data, 4)?,
+            unit: read_u32(data, 8)?,
+            bold: style & 1 != 0,
+            italic: style & 2 != 0,
+            underline: style & 4 != 0,
+            strikeout: style & 8 != 0,
+            family: read_utf16(data, 24, length)?,
+        })
+    }
+}
+
+/// Point type of a path point
+pub mod point_type {
+    pub const START: u8 = 0;
+    pub const LINE: u8 = 1;
+    pub const BEZIER: u8 = 3;
+    /// Mask of the type bits
+    pub const TYPE_MASK: u8 = 0x07;
+    /// The point ends a closed subpath
+    pub const CLOSE: u8 = 0x80;
+}
+
+/// Sequence of figures made of lines and cubic Bézier curves
+#[derive(Debug, Clone, PartialEq)]
+pub struct Path {
+    pub points: Vec<(f32, f32)>,
+    /// One [`point_type`] per point
+    pub types: Vec<u8>,
+}
+
+impl Path {
+    /// Parse an EmfPlusPath object
+    ///
+    /// Relative (EmfPlusPointR) coordinates are not supported.
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        let count = read_u32(data, 4)? as usize;
+        let flags = read_u32(data, 8)?;
+        if flags & 0x0800 != 0 || count > data.len() {
+            return None;
+        }
+
+        let compressed = flags & 0x4000 != 0;
+        let points = read_points(data, 12, count, compressed)?;
+        let mut offset = 12 + count * if compressed { 4 } else { 8 };
+
+        let mut types = Vec::with_capacity(count);
+        if flags & 0x1000 != 0 {
+            // Run-length encoded types: run count, then the point type
+            while types.len() < count {
+                let run = (*data.get(offset)? & 0x3F) as usize;
+                let kind = *data.get(offset + 1)?;
+                types.extend(std::iter::repeat_n(kind, run.max(1)));
+                offset += 2;
+            }
+            types.truncate(count);
+        } else {
+            types.extend_from_slice(data.get(offset..offset + count)?);
+        }
+        Some(Path { points, types })
+    }
+}
+
+/// EMF+ object stored in the object table
+#[derive(Debug, Clone)]
+pub enum EmfPlusObject {
+    Brush(Brush),
+    Pen(Pen),
+    Path(Path),
+    Font(Font),
+    /// Decoded bitmap image
+    Image(Arc<RgbaImage>),
+    /// Object type that is not used for rendering
+    Unsupported(u8),
+}
+
+impl EmfPlusObject {
+    /// Parse an object of the given type from its complete data
+    pub fn parse(kind: u8, data: &[u8]) -> Option<Self> {
+        match kind {
+            object_type::BRUSH => Brush::parse(data).map(EmfPlusObject::Brush),
+            object_type::PEN => Pen::parse(data).map(EmfPlusObject::Pen),
+            object_type::PATH => Path::parse(data).map(EmfPlusObject::Path),
+            object_type::FONT => Font::parse(data).map(EmfPlusObject::Font),
+            object_type::IMAGE => parse_image(data).map(|i| EmfPlusObject::Image(Arc::new(i))),
+            other => Some(EmfPlusObject::Unsupported(other)),
+        }
+    }
+}
+
+/// Decode an EmfPlusImage holding a bitmap
+///
+/// Metafile images are not supported.
+fn parse_image(data: &[u8]) -> Option<RgbaImage> {
+    const IMAGE_TYPE_BITMAP: u32 = 1;
+    const BITMAP_TYPE_COMPRESSED: u32 = 1;
+
+    if read_u32(data, 4)? != IMAGE_TYPE_BITMAP {
+        return None;
+    }
+    let width = read_i32(data, 8)?;
+    let height = read_i32(data, 12)?;
+    let stride = read_i32(data, 16)?.unsigned_abs() as usize;
+    let format = read_u32(data, 20)?;
+    let body = data.get(28..)?;
+
+    if read_u32(data, 24)? == BITMAP_TYPE_COMPRESSED {
+        return image::load_from_memory(body).ok().map(|i| i.to_rgba8());
+    }
+    if width <= 0 || height <= 0 || width > 32768 || height > 32768 {
+        return None;
+    }
+    decode_pixels(width as u32, height as u32, stride, format, body)
+}
+
+/// Decode uncompressed GDI+ pixel data (rows top-down)
+fn decode_pixels(
+    width: u32,
+    height: u32,
+    stride: usize,
+    format: u32,
+    body: &[u8],
+) -> Option<RgbaImage> {
+    const INDEXED: u32 = 0x0001_0000;
+
+    let bpp = ((format >> 8) & 0xFF) as usize;
+    let (palette, pixels) = if format & INDEXED != 0 {
+        // EmfPlusPalette: flags, count, ARGB entries
+        let count = read_u32(body, 4)? as usize;
+        let palette: Vec<[u8; 4]> = (0..count.min(256))
+            .map(|i| read_u32(body, 8 + i * 4).map(|c| Argb(c).to_rgba()))
+            .collect::<Option<_>>()?;
+        (palette, body.get(8 + count * 4..)?)
+    } else {
+        (Vec::new(), body)
+    };
+    if stride < (width as usize * bpp).div_ceil(8) || pixels.len() < stride * height as usize {
+        return None;
+    }
+
+    let mut image = RgbaImage::new(width, height);
+    for (y, row) in pixels.chunks(stride).take(height as usize).enumerate() {
+        for x in 0..width as usize {
+            let rgba = match (format, bpp) {
+                // 32bppARGB
+                (0x0026_200A, _) => [row[x * 4 + 2], row[x * 4 + 1], row[x * 4], row[x * 4 + 3]],
+                // 32bppPARGB
+                (0x000E_200B, _) => {
+                    let a = row[x * 4 + 3];
+                    let un = |c: u8| {
+                        if a == 0 {
+                            0
+                        } else {
+                            ((c as u32 * 255 + a as u32 / 2) / a as u32).min(255) as u8
+                        }
+                    };
+                    [un(row[x * 4 + 2]), un(row[x * 4 + 1]), un(row[x * 4]), a]
+                },
+                (_, 32) => [row[x * 4 + 2], row[x * 4 + 1], row[x * 4], 255],
+                (_, 24) => [row[x * 3 + 2], row[x * 3 + 1], row[x * 3], 255],
+                (_, 1 | 4 | 8) if !palette.is_empty() => {
+                    let bit = x * bpp;
+                    let shift = 8 - bpp - bit % 8;
+                    let index = (row[bit / 8] >> shift) as usize & ((1 << bpp) - 1);
+                    *palette.get(index).unwrap_or(&[0, 0, 0, 255])
+                },
+                _ => return None,
+            };
+            image.put_pixel(x as u32, y as u32, image::Rgba(rgba));
+        }
+    }
+    Some(image)
+}
+
+/// Read `count` points as PointF (or PointS when `compressed`)
+pub(crate) fn read_points(
+    data: &[u8],
+    offset: usize,
+    count: usize,
+    compressed: bool,
+) -> Option<Vec<(f32, f32)>> {
+    if count > data.len() {
+        return None;
+    }
+    (0..count)
+        .map(|i| {
+            if compressed {
+                let o = offset + i * 4;
+                Some((read_i16(data, o)? as f32, read_i16(data, o + 2)? as f32))
+            } else {
+                let o = offset + i * 8;
+                Some((read_f32(data, o)?, read_f32(data, o + 4)?))
+            }
+        })
+        .collect()
+}
+
+/// Read a RectF (or RectS when `compressed`) as `(x, y, width, height)`
+pub(crate) fn read_rect(
+    data: &[u8],
+    offset: usize,
+    compressed: bool,
+) -> Option<(f32, f32, f32, f32)> {
+    let p = read_points(data, offset, 2, compressed)?;
+    Some((p[0].0, p[0].1, p[1].0, p[1].1))
+}
+
+/// Read `length` UTF-16LE code units
+pub(crate) fn read_utf16(data: &[u8], offset: usize, length: usize) -> Option<String> {
+    let bytes = data.get(offset..offset.checked_add(length.checked_mul(2)?)?)?;
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    Some(String::from_utf16_lossy(&units))
+}
+
+/// Pixels per unit for a GDI+ unit at the given resolution
+///
+/// World, display and pixel units map to one device pixel.
+pub fn unit_to_pixels(unit: u32, dpi: f64) -> f64 {
+    match unit {
+        3 => dpi / 72.0,
+        4 => dpi,
+        5 => dpi / 300.0,
+        6 => dpi / 25.4,
+        _ => 1.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn words(values: &[u32]) -> Vec<u8> {
+        values.iter().flat_map(|v| v.to_le_bytes()).collect()
+    }
+
+    #[test]
+    fn test_parse_pen_skips_optional_data() {
+        // Version, type, flags (transform | line style | dashes), unit, width
+        let mut data = words(&[0xDBC0_1002, 0, 0x0121, 2, 3.0f32.to_bits()]);
+        data.extend(words(&[1.0f32.to_bits(); 6]));
+        data.extend(words(&[5, 2, 2.0f32.to_bits(), 1.0f32.to_bits()]));
+        data.extend(words(&[0xDBC0_1002, 0, 0xFF00_00FF]));
+
+        let pen = Pen::parse(&data).unwrap();
+        assert_eq!(pen.width, 3.0);
+        assert_eq!(pen.unit, 2);
+        assert_eq!(pen.dash, DashStyle::Custom(vec![2.0, 1.0]));
+        assert_eq!(pen.brush, Brush::Solid(Argb(0xFF00_00FF)));
+    }
+
+    #[test]
+    fn test_parse_path_with_rle_types() {
+        let mut data = words(&[0xDBC0_1002, 3, 0x5000]);
+        for v in [0i16, 0, 10, 0, 10, 10] {
+            data.extend(v.to_le_bytes());
+        }
+        data.extend([1, point_type::START, 2, point_type::LINE]);
+
+        let path = Path::parse(&data).unwrap();
+        assert_eq!(path.points, vec![(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)]);
+        assert_eq!(path.types, vec![0, 1, 1]);
+    }
+
+    #[test]
+    fn test_decode_argb_bitmap() {
+        let mut data = words(&[0xDBC0_1002, 1, 2, 1, 8, 0x0026_200A, 0]);
+        data.extend([0, 0, 255, 255, 255, 0, 0, 128]);
+
+        let EmfPlusObject::Image(image) = EmfPlusObject::parse(object_type::IMAGE, &data).unwrap()
+        else {
+            panic!("expected an image");
+        };
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(1, 0).0, [0, 0, 255, 128]);
+    }
+}
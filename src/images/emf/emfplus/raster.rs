@@ -0,0 +1,327 @@
+/// Raster output for EMF+ drawing operations
+///
+/// A small scanline rasterizer without anti-aliasing: paths are flattened to
+/// polygons and filled at pixel centers, strokes are filled as one quad per
+/// segment plus round joins, and images are sampled nearest-neighbour.
+/// Text is not rendered since no font rasterizer is available. Dash
+/// patterns are drawn solid.
+use super::interpreter::{DrawOp, Matrix, Paint, Segment};
+use image::{Rgba, RgbaImage};
+
+/// Line segments per flattened Bézier curve
+const CURVE_STEPS: usize = 16;
+
+type Polygon = Vec<(f64, f64)>;
+
+/// Render drawing operations onto `canvas`
+///
+/// `to_pixels` maps device coordinates to canvas pixels.
+pub fn render(canvas: &mut RgbaImage, ops: &[DrawOp], to_pixels: &Matrix) {
+    for op in ops {
+        match op {
+            DrawOp::Clear(color) => {
+                let rgba = Rgba(color.to_rgba());
+                for pixel in canvas.pixels_mut() {
+                    *pixel = rgba;
+                }
+            },
+            DrawOp::Fill { path, paint } => {
+                let polygons = flatten(path, to_pixels, true);
+                let paint = map_paint(paint, to_pixels);
+                fill_polygons(canvas, &polygons, &paint, true);
+            },
+            DrawOp::Stroke {
+                path, paint, width, ..
+            } => {
+                let width = (width * to_pixels.scale_factor()).max(1.0);
+                let polylines = flatten(path, to_pixels, false);
+                let paint = map_paint(paint, to_pixels);
+                let outline = stroke_outline(&polylines, width);
+                fill_polygons(canvas, &outline, &paint, false);
+            },
+            DrawOp::Image { image, transform } => {
+                draw_image(canvas, image, &transform.then(to_pixels));
+            },
+            DrawOp::Text { .. } => {},
+        }
+    }
+}
+
+fn map_paint(paint: &Paint, m: &Matrix) -> Paint {
+    match paint {
+        Paint::Solid(_) => paint.clone(),
+        Paint::Linear {
+            from,
+            to,
+            start,
+            end,
+        } => Paint::Linear {
+            from: m.apply(*from),
+            to: m.apply(*to),
+            start: *start,
+            end: *end,
+        },
+    }
+}
+
+/// Flatten a path into polygons (or open polylines) in pixel coordinates
+fn flatten(path: &[Segment], m: &Matrix, close_all: bool) -> Vec<Polygon> {
+    let mut out: Vec<Polygon> = Vec::new();
+    let mut current: Polygon = Vec::new();
+    let mut finish = |current: &mut Polygon, closed: bool| {
+        if current.len() >= 2 {
+            if closed && !close_all {
+                current.push(current[0]);
+            }
+            out.push(std::mem::take(current));
+        } else {
+            current.clear();
+        }
+    };
+
+    for segment in path {
+        match *segment {
+            Segment::MoveTo(x, y) => {
+                finish(&mut current, false);
+                current.push(m.apply((x, y)));
+            },
+            Segment::LineTo(x, y) => current.push(m.apply((x, y))),
+            Segment::CubicTo(c1, c2, p) => {
+                let Some(&p0) = current.last() else {
+                    continue;
+                };
+                let (c1, c2, p3) = (m.apply(c1), m.apply(c2), m.apply(p));
+                for i in 1..=CURVE_STEPS {
+                    let t = i as f64 / CURVE_STEPS as f64;
+                    let u = 1.0 - t;
+                    let (a, b, c, d) = (u * u * u, 3.0 * u * u * t, 3.0 * u * t * t, t * t * t);
+                    current.push((
+                        a * p0.0 + b * c1.0 + c * c2.0 + d * p3.0,
+                        a * p0.1 + b * c1.1 + c * c2.1 + d * p3.1,
+                    ));
+                }
+            },
+            Segment::Close => {
+                let start = current.first().copied();
+                finish(&mut current, true);
+                // Drawing continues from the start of the closed figure
+                current.extend(start);
+            },
+        }
+    }
+    finish(&mut current, false);
+    out
+}
+
+/// Outline of a stroke as polygons to be filled with the nonzero rule
+fn stroke_outline(polylines: &[Polygon], width: f64) -> Vec<Polygon> {
+    let half = width / 2.0;
+    let mut out = Vec::new();
+    for line in polylines {
+        for pair in line.windows(2) {
+            let ((x0, y0), (x1, y1)) = (pair[0], pair[1]);
+            let (dx, dy) = (x1 - x0, y1 - y0);
+            let len = (dx * dx + dy * dy).sqrt();
+            if len < 1e-9 {
+                continue;
+            }
+            let (nx, ny) = (-dy / len * half, dx / len * half);
+            out.push(vec![
+                (x0 + nx, y0 + ny),
+                (x1 + nx, y1 + ny),
+                (x1 - nx, y1 - ny),
+                (x0 - nx, y0 - ny),
+            ]);
+        }
+        // Round joins keep thick polylines free of notches
+        if width > 2.0 {
+            for &(x, y) in &line[1..line.len().saturating_sub(1)] {
+                out.push(
+                    (0..8)
+                        .map(|i| {
+                            let a = i as f64 * std::f64::consts::PI / 4.0;
+                            (x + half * a.cos(), y + half * a.sin())
+                        })
+                        .collect(),
+                );
+            }
+        }
+    }
+
+    // Orient all polygons the same way so overlaps do not cancel out
+    for polygon in &mut out {
+        if signed_area(polygon) < 0.0 {
+            polygon.reverse();
+        }
+    }
+    out
+}
+
+fn signed_area(polygon: &[(f64, f64)]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Fill polygons, sampling at pixel centers
+fn fill_polygons(canvas: &mut RgbaImage, polygons: &[Polygon], paint: &Paint, even_odd: bool) {
+    let (width, height) = canvas.dimensions();
+    let (min_y, max_y) = polygons
+        .iter()
+        .flatten()
+        .fold((f64::MAX, f64::MIN), |(lo, hi), p| {
+            (lo.min(p.1), hi.max(p.1))
+        });
+    if min_y > max_y {
+        return;
+    }
+    let first_row = (min_y - 0.5).ceil().max(0.0) as u32;
+    let last_row = ((max_y - 0.5).floor().min(height as f64 - 1.0)).max(-1.0);
+
+    let mut crossings: Vec<(f64, i32)> = Vec::new();
+    let mut row = first_row as f64;
+    while row <= last_row {
+        let y = row + 0.5;
+        crossings.clear();
+        for polygon in polygons {
+            let n = polygon.len();
+            for i in 0..n {
+                let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+                if (a.1 <= y) != (b.1 <= y) {
+                    let x = a.0 + (y - a.1) / (b.1 - a.1) * (b.0 - a.0);
+                    crossings.push((x, if b.1 > a.1 { 1 } else { -1 }));
+                }
+            }
+        }
+        crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let mut winding = 0;
+        for pair in crossings.windows(2) {
+            winding += pair[0].1;
+            let inside = if even_odd {
+                winding % 2 != 0
+            } else {
+                winding != 0
+            };
+            if !inside {
+                continue;
+            }
+            let from = (pair[0].0 - 0.5).ceil().max(0.0) as u32;
+            let to = ((pair[1].0 - 0.5).ceil().min(width as f64)).max(0.0) as u32;
+            for x in from..to {
+                let color = sample(paint, (x as f64 + 0.5, y));
+                blend(canvas.get_pixel_mut(x, row as u32), color);
+            }
+        }
+        row += 1.0;
+    }
+}
+
+fn sample(paint: &Paint, (x, y): (f64, f64)) -> [u8; 4] {
+    match paint {
+        Paint::Solid(color) => color.to_rgba(),
+        Paint::Linear {
+            from,
+            to,
+            start,
+            end,
+        } => {
+            let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+            let len2 = dx * dx + dy * dy;
+            let t = if len2 > 0.0 {
+                (((x - from.0) * dx + (y - from.1) * dy) / len2).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let (s, e) = (start.to_rgba(), end.to_rgba());
+            std::array::from_fn(|i| (s[i] as f64 + (e[i] as f64 - s[i] as f64) * t).round() as u8)
+        },
+    }
+}
+
+/// Source-over blending
+fn blend(dst: &mut Rgba<u8>, src: [u8; 4]) {
+    let alpha = src[3] as u32;
+    if alpha == 255 {
+        dst.0 = src;
+        return;
+    }
+    if alpha == 0 {
+        return;
+    }
+    let inv = 255 - alpha;
+    for (d, s) in dst.0.iter_mut().zip(src).take(3) {
+        *d = ((s as u32 * alpha + *d as u32 * inv) / 255) as u8;
+    }
+    dst.0[3] = (alpha + dst.0[3] as u32 * inv / 255).min(255) as u8;
+}
+
+/// Draw an image whose pixels map to canvas pixels through `m`
+fn draw_image(canvas: &mut RgbaImage, image: &RgbaImage, m: &Matrix) {
+    let Some(inverse) = m.invert() else {
+        return;
+    };
+    let (iw, ih) = (image.width() as f64, image.height() as f64);
+    let corners = [(0.0, 0.0), (iw, 0.0), (0.0, ih), (iw, ih)].map(|p| m.apply(p));
+    let (min_x, max_x, min_y, max_y) = corners.iter().fold(
+        (f64::MAX, f64::MIN, f64::MAX, f64::MIN),
+        |(a, b, c, d), p| (a.min(p.0), b.max(p.0), c.min(p.1), d.max(p.1)),
+    );
+
+    let (width, height) = canvas.dimensions();
+    let x_range = min_x.floor().max(0.0) as u32..(max_x.ceil().min(width as f64)).max(0.0) as u32;
+    let y_range = min_y.floor().max(0.0) as u32..(max_y.ceil().min(height as f64)).max(0.0) as u32;
+    for y in y_range {
+        for x in x_range.clone() {
+            let (u, v) = inverse.apply((x as f64 + 0.5, y as f64 + 0.5));
+            if u < 0.0 || v < 0.0 || u >= iw || v >= ih {
+                continue;
+            }
+            let src = image.get_pixel(u as u32, v as u32).0;
+            blend(canvas.get_pixel_mut(x, y), src);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::images::emf::emfplus::objects::Argb;
+
+    #[test]
+    fn test_fill_and_stroke() {
+        let mut canvas = RgbaImage::from_pixel(20, 20, Rgba([255, 255, 255, 255]));
+        let square = vec![
+            Segment::MoveTo(2.0, 2.0),
+            Segment::LineTo(10.0, 2.0),
+            Segment::LineTo(10.0, 10.0),
+            Segment::LineTo(2.0, 10.0),
+            Segment::Close,
+        ];
+        let ops = [
+            DrawOp::Fill {
+                path: square,
+                paint: Paint::Solid(Argb(0xFFFF_0000)),
+            },
+            DrawOp::Stroke {
+                path: vec![Segment::MoveTo(0.0, 15.0), Segment::LineTo(20.0, 15.0)],
+                paint: Paint::Solid(Argb(0x800000FF)),
+                width: 2.0,
+                dash: None,
+            },
+        ];
+        render(&mut canvas, &ops, &Matrix::IDENTITY);
+
+        assert_eq!(canvas.get_pixel(2, 2).0, [255, 0, 0, 255]);
+        assert_eq!(canvas.get_pixel(9, 9).0, [255, 0, 0, 255]);
+        assert_eq!(canvas.get_pixel(10, 10).0, [255, 255, 255, 255]);
+        assert_eq!(canvas.get_pixel(5, 14).0, [127, 127, 255, 255]);
+        assert_eq!(canvas.get_pixel(5, 15).0, [127, 127, 255, 255]);
+        assert_eq!(canvas.get_pixel(5, 16).0, [255, 255, 255, 255]);
+    }
+}
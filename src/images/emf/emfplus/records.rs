@@ -0,0 +1,178 @@
+/// EMF+ record framing
+///
+/// EMF+ records travel inside EMR_COMMENT records whose comment identifier is
+/// "EMF+". Each comment may carry several EMF+ records, each with a 12-byte
+/// header (type, flags, size, data size) followed by its data.
+use crate::images::emf::parser::EmfRecord;
+use crate::images::emf::records::EmrType;
+
+/// Comment identifier of EMF+ comments ("EMF+" in little-endian)
+pub const EMF_PLUS_SIGNATURE: u32 = 0x2B46_4D45;
+
+/// EMF+ record types
+pub mod record_type {
+    pub const HEADER: u16 = 0x4001;
+    pub const END_OF_FILE: u16 = 0x4002;
+    pub const COMMENT: u16 = 0x4003;
+    pub const GET_DC: u16 = 0x4004;
+    pub const OBJECT: u16 = 0x4008;
+    pub const CLEAR: u16 = 0x4009;
+    pub const FILL_RECTS: u16 = 0x400A;
+    pub const DRAW_RECTS: u16 = 0x400B;
+    pub const FILL_POLYGON: u16 = 0x400C;
+    pub const DRAW_LINES: u16 = 0x400D;
+    pub const FILL_ELLIPSE: u16 = 0x400E;
+    pub const DRAW_ELLIPSE: u16 = 0x400F;
+    pub const FILL_PIE: u16 = 0x4010;
+    pub const DRAW_PIE: u16 = 0x4011;
+    pub const DRAW_ARC: u16 = 0x4012;
+    pub const FILL_REGION: u16 = 0x4013;
+    pub const FILL_PATH: u16 = 0x4014;
+    pub const DRAW_PATH: u16 = 0x4015;
+    pub const FILL_CLOSED_CURVE: u16 = 0x4016;
+    pub const DRAW_CLOSED_CURVE: u16 = 0x4017;
+    pub const DRAW_CURVE: u16 = 0x4018;
+    pub const DRAW_BEZIERS: u16 = 0x4019;
+    pub const DRAW_IMAGE: u16 = 0x401A;
+    pub const DRAW_IMAGE_POINTS: u16 = 0x401B;
+    pub const DRAW_STRING: u16 = 0x401C;
+    pub const SET_RENDERING_ORIGIN: u16 = 0x401D;
+    pub const SET_ANTI_ALIAS_MODE: u16 = 0x401E;
+    pub const SET_TEXT_RENDERING_HINT: u16 = 0x401F;
+    pub const SET_TEXT_CONTRAST: u16 = 0x4020;
+    pub const SET_INTERPOLATION_MODE: u16 = 0x4021;
+    pub const SET_PIXEL_OFFSET_MODE: u16 = 0x4022;
+    pub const SET_COMPOSITING_MODE: u16 = 0x4023;
+    pub const SET_COMPOSITING_QUALITY: u16 = 0x4024;
+    pub const SAVE: u16 = 0x4025;
+    pub const RESTORE: u16 = 0x4026;
+    pub const BEGIN_CONTAINER: u16 = 0x4027;
+    pub const BEGIN_CONTAINER_NO_PARAMS: u16 = 0x4028;
+    pub const END_CONTAINER: u16 = 0x4029;
+    pub const SET_WORLD_TRANSFORM: u16 = 0x402A;
+    pub const RESET_WORLD_TRANSFORM: u16 = 0x402B;
+    pub const MULTIPLY_WORLD_TRANSFORM: u16 = 0x402C;
+    pub const TRANSLATE_WORLD_TRANSFORM: u16 = 0x402D;
+    pub const SCALE_WORLD_TRANSFORM: u16 = 0x402E;
+    pub const ROTATE_WORLD_TRANSFORM: u16 = 0x402F;
+    pub const SET_PAGE_TRANSFORM: u16 = 0x4030;
+    pub const RESET_CLIP: u16 = 0x4031;
+    pub const SET_CLIP_RECT: u16 = 0x4032;
+    pub const SET_CLIP_PATH: u16 = 0x4033;
+    pub const SET_CLIP_REGION: u16 = 0x4034;
+    pub const OFFSET_CLIP: u16 = 0x4035;
+    pub const DRAW_DRIVER_STRING: u16 = 0x4036;
+    pub const STROKE_FILL_PATH: u16 = 0x4037;
+    pub const SERIALIZABLE_OBJECT: u16 = 0x4038;
+    pub const SET_TS_GRAPHICS: u16 = 0x4039;
+    pub const SET_TS_CLIP: u16 = 0x403A;
+}
+
+/// Record flag bits shared by several drawing records
+pub mod flags {
+    /// The brush is given as an ARGB color instead of an object id
+    pub const SOLID_COLOR: u16 = 0x8000;
+    /// Coordinates are 16-bit integers instead of 32-bit floats
+    pub const COMPRESSED: u16 = 0x4000;
+    /// DrawLines: close the figure
+    pub const CLOSED: u16 = 0x2000;
+    /// Transform records: post-multiply (append) the matrix
+    pub const APPEND: u16 = 0x2000;
+    /// Coordinates are relative (EmfPlusPointR)
+    pub const RELATIVE: u16 = 0x0800;
+    /// Object records: the object continues in the next record
+    pub const CONTINUE_OBJECT: u16 = 0x8000;
+}
+
+/// A single EMF+ record
+#[derive(Debug, Clone)]
+pub struct EmfPlusRecord {
+    /// Record type (see [`record_type`])
+    pub record_type: u16,
+    /// Record-specific flags
+    pub flags: u16,
+    /// Record data (excluding the 12-byte header)
+    pub data: Vec<u8>,
+}
+
+impl EmfPlusRecord {
+    /// Collect the EMF+ records embedded in the comments of an EMF
+    pub fn extract(records: &[EmfRecord]) -> Vec<EmfPlusRecord> {
+        let mut out = Vec::new();
+        for record in records {
+            if EmrType::from_u32(record.record_type) == Some(EmrType::Comment) {
+                Self::parse_comment(&record.data, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Parse the EMF+ records of one EMR_COMMENT (data excluding type/size)
+    ///
+    /// Comments without the EMF+ identifier are ignored.
+    pub fn parse_comment(data: &[u8], out: &mut Vec<EmfPlusRecord>) {
+        let (Some(data_size), Some(EMF_PLUS_SIGNATURE)) = (read_u32(data, 0), read_u32(data, 4))
+        else {
+            return;
+        };
+        let end = (4 + data_size as usize).min(data.len());
+        let mut offset = 8;
+
+        while offset + 12 <= end {
+            let record_type = read_u16(data, offset).unwrap_or(0);
+            let flags = read_u16(data, offset + 2).unwrap_or(0);
+            let size = read_u32(data, offset + 4).unwrap_or(0) as usize;
+            let data_size = read_u32(data, offset + 8).unwrap_or(0) as usize;
+            if size < 12 || offset + size > end {
+                break;
+            }
+
+            let body_end = (offset + 12 + data_size).min(offset + size);
+            out.push(EmfPlusRecord {
+                record_type,
+                flags,
+                data: data[offset + 12..body_end].to_vec(),
+            });
+            offset += size;
+        }
+    }
+
+    /// Object id carried in the low byte of the flags
+    #[inline]
+    pub fn object_id(&self) -> u8 {
+        (self.flags & 0xFF) as u8
+    }
+
+    /// Whether `flag` (see [`flags`]) is set
+    #[inline]
+    pub fn has_flag(&self, flag: u16) -> bool {
+        self.flags & flag != 0
+    }
+}
+
+#[inline]
+pub(crate) fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    data.get(offset..offset + 2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+}
+
+#[inline]
+pub(crate) fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    read_u16(data, offset).map(|v| v as i16)
+}
+
+#[inline]
+pub(crate) fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    data.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+#[inline]
+pub(crate) fn read_i32(data: &[u8], offset: usize) -> Option<i32> {
+    read_u32(data, offset).map(|v| v as i32)
+}
+
+#[inline]
+pub(crate) fn read_f32(data: &[u8], offset: usize) -> Option<f32> {
+    read_u32(data, offset).map(f32::from_bits)
+}
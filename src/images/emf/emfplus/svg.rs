@@ -0,0 +1,236 @@
+/// SVG output for EMF+ drawing operations
+use super::interpreter::{DrawOp, Matrix, Paint, Segment};
+use super::objects::Argb;
+use crate::images::dib::encode_png;
+use crate::images::svg_utils::{write_num, write_png_image, write_xml_escaped};
+use std::fmt::Write;
+
+/// Convert drawing operations to SVG elements
+///
+/// `bounds` is the drawing surface `(x, y, width, height)` in device units,
+/// used by clear operations.
+pub fn svg_elements(ops: &[DrawOp], bounds: (f64, f64, f64, f64)) -> Vec<String> {
+    let mut gradients = 0;
+    let mut elements = Vec::with_capacity(ops.len());
+
+    for op in ops {
+        let mut s = String::with_capacity(128);
+        match op {
+            DrawOp::Clear(color) => {
+                let (x, y, w, h) = bounds;
+                s.push_str("<rect x=\"");
+                write_num(&mut s, x);
+                s.push_str("\" y=\"");
+                write_num(&mut s, y);
+                s.push_str("\" width=\"");
+                write_num(&mut s, w);
+                s.push_str("\" height=\"");
+                write_num(&mut s, h);
+                s.push('"');
+                write_paint(&mut s, "fill", &Paint::Solid(*color), None);
+                s.push_str("/>");
+            },
+            DrawOp::Fill { path, paint } => {
+                let id = gradient_def(&mut s, paint, &mut gradients);
+                s.push_str("<path d=\"");
+                write_path(&mut s, path);
+                s.push('"');
+                write_paint(&mut s, "fill", paint, id);
+                s.push_str(" fill-rule=\"evenodd\"/>");
+            },
+            DrawOp::Stroke {
+                path,
+                paint,
+                width,
+                dash,
+            } => {
+                let id = gradient_def(&mut s, paint, &mut gradients);
+                s.push_str("<path d=\"");
+                write_path(&mut s, path);
+                s.push_str("\" fill=\"none\"");
+                write_paint(&mut s, "stroke", paint, id);
+                s.push_str(" stroke-width=\"");
+                write_num(&mut s, *width);
+                s.push('"');
+                if let Some(dash) = dash {
+                    s.push_str(" stroke-dasharray=\"");
+                    for (i, d) in dash.iter().enumerate() {
+                        if i > 0 {
+                            s.push(',');
+                        }
+                        write_num(&mut s, *d);
+                    }
+                    s.push('"');
+                }
+                s.push_str("/>");
+            },
+            DrawOp::Image { image, transform } => {
+                let Ok(png) = encode_png(image) else {
+                    continue;
+                };
+                s.push_str("<g transform=\"");
+                write_matrix(&mut s, transform);
+                s.push_str("\">");
+                write_png_image(
+                    &mut s,
+                    0.0,
+                    0.0,
+                    image.width() as f64,
+                    image.height() as f64,
+                    &png,
+                );
+                s.push_str("</g>");
+            },
+            DrawOp::Text {
+                text,
+                origin,
+                size,
+                rotation,
+                font,
+                color,
+            } => {
+                s.push_str("<text x=\"");
+                write_num(&mut s, origin.0);
+                s.push_str("\" y=\"");
+                write_num(&mut s, origin.1);
+                s.push_str("\" font-family=\"");
+                write_xml_escaped(&mut s, &font.family);
+                s.push_str("\" font-size=\"");
+                write_num(&mut s, *size);
+                s.push('"');
+                write_paint(&mut s, "fill", &Paint::Solid(*color), None);
+                if font.bold {
+                    s.push_str(" font-weight=\"bold\"");
+                }
+                if font.italic {
+                    s.push_str(" font-style=\"italic\"");
+                }
+                match (font.underline, font.strikeout) {
+                    (true, true) => s.push_str(" text-decoration=\"underline line-through\""),
+                    (true, false) => s.push_str(" text-decoration=\"underline\""),
+                    (false, true) => s.push_str(" text-decoration=\"line-through\""),
+                    (false, false) => {},
+                }
+                if rotation.abs() > 0.01 {
+                    s.push_str(" transform=\"rotate(");
+                    write_num(&mut s, *rotation);
+                    s.push(' ');
+                    write_num(&mut s, origin.0);
+                    s.push(' ');
+                    write_num(&mut s, origin.1);
+                    s.push_str(")\"");
+                }
+                s.push('>');
+                write_xml_escaped(&mut s, text);
+                s.push_str("</text>");
+            },
+        }
+        elements.push(s);
+    }
+    elements
+}
+
+fn write_path(s: &mut String, path: &[Segment]) {
+    let point = |s: &mut String, cmd: char, (x, y): (f64, f64)| {
+        s.push(cmd);
+        write_num(s, x);
+        s.push(' ');
+        write_num(s, y);
+    };
+    for segment in path {
+        match *segment {
+            Segment::MoveTo(x, y) => point(s, 'M', (x, y)),
+            Segment::LineTo(x, y) => point(s, 'L', (x, y)),
+            Segment::CubicTo(c1, c2, p) => {
+                point(s, 'C', c1);
+                point(s, ' ', c2);
+                point(s, ' ', p);
+            },
+            Segment::Close => s.push('Z'),
+        }
+    }
+}
+
+fn write_matrix(s: &mut String, m: &Matrix) {
+    s.push_str("matrix(");
+    for (i, v) in m.0.iter().enumerate() {
+        if i > 0 {
+            s.push(' ');
+        }
+        // Keep more precision than write_num for scale factors
+        let _ = write!(s, "{}", (v * 1e6).round() / 1e6);
+    }
+    s.push(')');
+}
+
+fn write_color(s: &mut String, color: Argb) {
+    let _ = write!(
+        s,
+        "#{:02x}{:02x}{:02x}",
+        color.red(),
+        color.green(),
+        color.blue()
+    );
+}
+
+/// Write a `<linearGradient>` for gradient paints, returning its id
+fn gradient_def(s: &mut String, paint: &Paint, count: &mut usize) -> Option<usize> {
+    let Paint::Linear {
+        from,
+        to,
+        start,
+        end,
+    } = paint
+    else {
+        return None;
+    };
+    *count += 1;
+    let _ = write!(
+        s,
+        "<defs><linearGradient id=\"emfplus-gradient-{}\" gradientUnits=\"userSpaceOnUse\" x1=\"",
+        count
+    );
+    write_num(s, from.0);
+    s.push_str("\" y1=\"");
+    write_num(s, from.1);
+    s.push_str("\" x2=\"");
+    write_num(s, to.0);
+    s.push_str("\" y2=\"");
+    write_num(s, to.1);
+    s.push_str("\">");
+    for (offset, color) in [("0", start), ("1", end)] {
+        let _ = write!(s, "<stop offset=\"{}\" stop-color=\"", offset);
+        write_color(s, *color);
+        s.push('"');
+        write_opacity(s, "stop-opacity", *color);
+        s.push_str("/>");
+    }
+    s.push_str("</linearGradient></defs>");
+    Some(*count)
+}
+
+fn write_paint(s: &mut String, attr: &str, paint: &Paint, gradient: Option<usize>) {
+    let _ = write!(s, " {}=\"", attr);
+    match (paint, gradient) {
+        (Paint::Solid(color), _) => {
+            write_color(s, *color);
+            s.push('"');
+            write_opacity(s, &format!("{}-opacity", attr), *color);
+        },
+        (Paint::Linear { .. }, Some(id)) => {
+            let _ = write!(s, "url(#emfplus-gradient-{})\"", id);
+        },
+        (Paint::Linear { start, .. }, None) => {
+            write_color(s, *start);
+            s.push('"');
+        },
+    }
+}
+
+fn write_opacity(s: &mut String, attr: &str, color: Argb) {
+    if color.alpha() != 255 {
+        let _ = write!(s, " {}=\"", attr);
+        write_num(s, color.alpha() as f64 / 255.0);
+        s.push('"');
+    }
+}
@@ -10,8 +10,10 @@
 // - [MS-EMF]: Enhanced Metafile Format Specification
 // - https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-emf/
 
+mod bitmap;
 pub mod converter;
 pub mod device_context;
+pub mod emfplus;
 pub mod gdi_objects;
 pub mod parser;
 pub mod record_parser;
@@ -26,6 +28,7 @@ pub use record_parser::*;
 pub use svg::EmfSvgConverter;
 
 use crate::common::error::Result;
+use crate::common::trace;
 use image::ImageFormat;
 
 /// Convert EMF data to a raster image in the specified format
@@ -55,11 +58,19 @@ pub fn convert_emf(
     width: Option<u32>,
     height: Option<u32>,
 ) -> Result<Vec<u8>> {
+    let _span = trace::debug_span!(
+        "convert_image",
+        source = "emf",
+        target = ?format,
+        size = emf_data.len()
+    )
+    .entered();
     let parser = EmfParser::new(emf_data)?;
     let options = EmfToRasterOptions {
         width,
         height,
         background_color: image::Rgba([255, 255, 255, 255]),
+        force_gdi: false,
     };
 
     let converter = EmfConverter::new(parser, options);
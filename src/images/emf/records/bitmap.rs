@@ -1,4 +1,8 @@
 /// EMF Bitmap Operation Records
+///
+/// Bitmap offsets (`off_bmi_src`, `off_bits_src`) are relative to the start of
+/// the record, including the 8-byte type/size prefix that is not part of these
+/// headers.
 use super::types::{RectL, XForm};
 use zerocopy::{FromBytes, IntoBytes};
 
@@ -29,12 +33,10 @@ pub mod rop {
     pub const WHITENESS: u32 = 0x00FF0062; // dest = WHITE
 }
 
-/// EMR_BITBLT header
+/// EMR_BITBLT header (without record type/size which are parsed separately)
 #[derive(Debug, Clone, Copy, IntoBytes, FromBytes)]
 #[repr(C)]
 pub struct EmrBitBltHeader {
-    pub record_type: u32,
-    pub record_size: u32,
     pub bounds: RectL,
     pub x_dest: i32,
     pub y_dest: i32,
@@ -53,12 +55,10 @@ pub struct EmrBitBltHeader {
     // Followed by bitmap data if present
 }
 
-/// EMR_STRETCHBLT header
+/// EMR_STRETCHBLT header (without record type/size which are parsed separately)
 #[derive(Debug, Clone, Copy, IntoBytes, FromBytes)]
 #[repr(C)]
 pub struct EmrStretchBltHeader {
-    pub record_type: u32,
-    pub record_size: u32,
     pub bounds: RectL,
     pub x_dest: i32,
     pub y_dest: i32,
@@ -79,12 +79,10 @@ pub struct EmrStretchBltHeader {
     // Followed by bitmap data if present
 }
 
-/// EMR_ALPHABLEND header
+/// EMR_ALPHABLEND header (without record type/size which are parsed separately)
 #[derive(Debug, Clone, Copy, IntoBytes, FromBytes)]
 #[repr(C)]
 pub struct EmrAlphaBlendHeader {
-    pub record_type: u32,
-    pub record_size: u32,
     pub bounds: RectL,
     pub x_dest: i32,
     pub y_dest: i32,
@@ -115,12 +113,10 @@ pub struct BlendFunction {
     pub alpha_format: u8,       // 0 or 1 (AC_SRC_ALPHA)
 }
 
-/// EMR_TRANSPARENTBLT header
+/// EMR_TRANSPARENTBLT header (without record type/size which are parsed separately)
 #[derive(Debug, Clone, Copy, IntoBytes, FromBytes)]
 #[repr(C)]
 pub struct EmrTransparentBltHeader {
-    pub record_type: u32,
-    pub record_size: u32,
     pub bounds: RectL,
     pub x_dest: i32,
     pub y_dest: i32,
@@ -141,12 +137,10 @@ pub struct EmrTransparentBltHeader {
     // Followed by bitmap data
 }
 
-/// EMR_STRETCHDIBITS header
+/// EMR_STRETCHDIBITS header (without record type/size which are parsed separately)
 #[derive(Debug, Clone, Copy, IntoBytes, FromBytes)]
 #[repr(C)]
 pub struct EmrStretchDiBitsHeader {
-    pub record_type: u32,
-    pub record_size: u32,
     pub bounds: RectL,
     pub x_dest: i32,
     pub y_dest: i32,
@@ -165,12 +159,10 @@ pub struct EmrStretchDiBitsHeader {
     // Followed by bitmap data
 }
 
-/// EMR_SETDIBITSTODEVICE header
+/// EMR_SETDIBITSTODEVICE header (without record type/size which are parsed separately)
 #[derive(Debug, Clone, Copy, IntoBytes, FromBytes)]
 #[repr(C)]
 pub struct EmrSetDiBitsToDeviceHeader {
-    pub record_type: u32,
-    pub record_size: u32,
     pub bounds: RectL,
     pub x_dest: i32,
     pub y_dest: i32,
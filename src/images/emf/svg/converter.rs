@@ -15,25 +15,60 @@ use super::{
 };
 use crate::common::error::Result;
 use crate::common::xml::escape::escape_xml;
+use crate::images::emf::bitmap::DibBlit;
+use crate::images::emf::emfplus;
 use crate::images::emf::parser::EmfParser;
 use crate::images::emf::records::*;
-use crate::images::svg_utils::write_num;
+use crate::images::svg_utils::{bitmap_image_element, write_num};
+use image::RgbaImage;
 use std::fmt::Write;
 use zerocopy::FromBytes;
 
+/// A decoded bitmap with its destination corners in SVG coordinates
+pub(crate) type PlacedBitmap = ((f64, f64), (f64, f64), RgbaImage);
+
 /// EMF to SVG Converter with in-place optimization
 pub struct EmfSvgConverter<'a> {
     parser: &'a EmfParser,
+    force_gdi: bool,
 }
 
 impl<'a> EmfSvgConverter<'a> {
     /// Create new converter
     pub fn new(parser: &'a EmfParser) -> Self {
-        Self { parser }
+        Self {
+            parser,
+            force_gdi: false,
+        }
+    }
+
+    /// Render the GDI records even when the file carries an EMF+ stream
+    pub fn force_gdi(mut self, force: bool) -> Self {
+        self.force_gdi = force;
+        self
     }
 
     /// Convert EMF to SVG with in-place optimizations
+    ///
+    /// Files with an EMF+ stream that draws anything are converted from
+    /// their EMF+ records unless [`force_gdi`](Self::force_gdi) is set.
     pub fn convert(&self) -> Result<String> {
+        if !self.force_gdi
+            && let Some(ops) = emfplus::draw_ops(self.parser)
+        {
+            let (left, top, _, _) = self.parser.header.bounds;
+            let bounds = (
+                left as f64,
+                top as f64,
+                self.parser.header.width() as f64,
+                self.parser.header.height() as f64,
+            );
+            return self.build_svg(
+                &emfplus::svg::svg_elements(&ops, bounds),
+                &RenderState::new(),
+            );
+        }
+
         let mut state = RenderState::new();
         let mut buffer = ElementBuffer::new();
 
@@ -53,6 +88,34 @@ impl<'a> EmfSvgConverter<'a> {
         self.build_svg(&buffer.elements, &state)
     }
 
+    /// Bitmaps drawn by the EMF, with their destination corners in SVG
+    /// coordinates, for raster rendering
+    pub(crate) fn placed_bitmaps(&self) -> Vec<PlacedBitmap> {
+        let mut state = RenderState::new();
+        let mut placed = Vec::new();
+
+        for record in &self.parser.records {
+            match DibBlit::parse(record.record_type, &record.data) {
+                Some(blit) => {
+                    if let Some(image) = blit.image() {
+                        let ((left, top), (right, bottom)) = blit.dest_corners();
+                        placed.push((
+                            state.dc.transform_point(left, top),
+                            state.dc.transform_point(right, bottom),
+                            image,
+                        ));
+                    }
+                },
+                // Other records only matter for the state they update
+                None => {
+                    let _ = self.process_record(record, &mut state);
+                },
+            }
+        }
+
+        placed
+    }
+
     /// Process a single EMF record
     fn process_record(
         &self,
@@ -330,6 +393,11 @@ impl<'a> EmfSvgConverter<'a> {
                 self.render_text(record, state, record_type == Some(EmrType::ExtTextOutW))
             },
 
+            // Embedded bitmaps
+            Some(EmrType::BitBlt) | Some(EmrType::StretchBlt) | Some(EmrType::StretchDIBits) => {
+                Ok(self.render_bitmap(record, state).map(|svg| vec![svg]))
+            },
+
             // Unimplemented records - log but don't error
             _ => Ok(None),
         }
@@ -523,6 +591,23 @@ impl<'a> EmfSvgConverter<'a> {
         }
     }
 
+    /// Render a bitmap record as an embedded PNG image
+    fn render_bitmap(
+        &self,
+        record: &super::super::parser::EmfRecord,
+        state: &RenderState,
+    ) -> Option<String> {
+        let blit = DibBlit::parse(record.record_type, &record.data)?;
+        let image = blit.image()?;
+        let ((left, top), (right, bottom)) = blit.dest_corners();
+
+        bitmap_image_element(
+            state.dc.transform_point(left, top),
+            state.dc.transform_point(right, bottom),
+            &image,
+        )
+    }
+
     /// Render text
     fn render_text(
         &self,
@@ -4,6 +4,7 @@
 // from Microsoft Office documents (PPT, DOC) using the Escher drawing layer.
 
 use crate::common::error::Result;
+use crate::common::trace;
 use crate::images::{Blip, BlipStore, BlipStoreEntry};
 use crate::ole::escher::EscherRecord;
 use crate::ole::ppt::escher::{EscherContainer, EscherParser, EscherRecordType};
@@ -194,9 +195,9 @@ impl ImageExtractor {
                     if child.record_type == EscherRecordType::BSE {
                         match BlipStoreEntry::parse(child.data) {
                             Ok(bse) => store.add_entry(bse),
-                            Err(e) => {
+                            Err(_err) => {
                                 // Log error but continue processing
-                                eprintln!("Warning: Failed to parse BSE entry: {}", e);
+                                trace::warn!(error = %_err, "skipping unparsable BSE entry");
                             },
                         }
                     }
@@ -379,8 +380,8 @@ impl ImageExtractor {
                         images.push(ExtractedImage::new(owned_blip, name, index));
                         index += 1;
                     },
-                    Err(e) => {
-                        eprintln!("Warning: Failed to parse BLIP at index {}: {}", index, e);
+                    Err(_err) => {
+                        trace::warn!(index, error = %_err, "skipping unparsable BLIP record");
                     },
                 }
             }
@@ -607,8 +608,8 @@ pub mod ppt {
                             index: img.index,
                         }));
                     },
-                    Err(e) => {
-                        eprintln!("Warning: Failed to read Pictures stream: {}", e);
+                    Err(_err) => {
+                        trace::warn!(error = %_err, "failed to read Pictures stream");
                     },
                 }
             }
@@ -628,8 +629,8 @@ pub mod ppt {
                             }
                         }));
                     },
-                    Err(e) => {
-                        eprintln!("Warning: Failed to read PowerPoint Document stream: {}", e);
+                    Err(_err) => {
+                        trace::warn!(error = %_err, "failed to read PowerPoint Document stream");
                     },
                 }
             }
@@ -674,16 +675,16 @@ pub mod doc {
                             Ok(images) => {
                                 all_images.extend(images);
                             },
-                            Err(e) => {
-                                eprintln!(
-                                    "Warning: Failed to search for BLIPs in Data stream: {}",
-                                    e
+                            Err(_err) => {
+                                trace::warn!(
+                                    error = %_err,
+                                    "failed to search for BLIPs in Data stream"
                                 );
                             },
                         }
                     },
-                    Err(e) => {
-                        eprintln!("Warning: Failed to read Data stream: {}", e);
+                    Err(_err) => {
+                        trace::warn!(error = %_err, "failed to read Data stream");
                     },
                 }
             }
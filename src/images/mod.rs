@@ -15,7 +15,8 @@
 //
 // - `blip`: Core BLIP (Binary Large Image or Picture) record parsing
 // - `bse`: BLIP Store Entry (BSE) metadata parsing
-// - `emf`: Enhanced Metafile (EMF) format support
+// - `dib`: Device Independent Bitmap decoding for embedded raster records
+// - `emf`: Enhanced Metafile (EMF) format support, including EMF+ records
 // - `wmf`: Windows Metafile (WMF) format support
 // - `pict`: Macintosh PICT format support
 // - `extractor`: High-level image extraction from Office files
@@ -95,6 +96,7 @@
 /// - Corrupted metafile data (returns error but continues extraction)
 pub mod blip;
 pub mod bse;
+pub mod dib;
 pub mod emf;
 pub mod extractor;
 pub mod pict;
@@ -103,6 +105,7 @@ pub mod svg_utils;
 pub mod wmf;
 
 use crate::common::error::Result;
+use crate::common::trace;
 pub use blip::{BitmapBlip, Blip, BlipType, MetafileBlip, RecordHeader};
 pub use bse::{BlipStore, BlipStoreEntry};
 pub use extractor::{ExtractedImage, ImageExtractor};
@@ -142,6 +145,13 @@ pub fn convert_blip_to_format<'data>(
         Blip::Bitmap(bitmap) => {
             // For bitmap formats that are already in a modern format, we may just need
             // to re-encode or pass through
+            let _span = trace::debug_span!(
+                "convert_image",
+                source = "bitmap",
+                target = ?format,
+                size = bitmap.picture_data.len()
+            )
+            .entered();
             let img = image::load_from_memory(&bitmap.picture_data[..]).map_err(|e| {
                 crate::common::error::Error::ParseError(format!("Failed to load bitmap: {}", e))
             })?;
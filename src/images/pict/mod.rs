@@ -23,6 +23,7 @@ pub use converter::{PictConverter, PictToRasterOptions};
 pub use parser::{PictParser, PictVersion};
 
 use crate::common::error::Result;
+use crate::common::trace;
 use image::ImageFormat;
 
 /// Convert PICT data to a raster image in the specified format
@@ -52,6 +53,13 @@ pub fn convert_pict(
     width: Option<u32>,
     height: Option<u32>,
 ) -> Result<Vec<u8>> {
+    let _span = trace::debug_span!(
+        "convert_image",
+        source = "pict",
+        target = ?format,
+        size = pict_data.len()
+    )
+    .entered();
     let parser = PictParser::new(pict_data)?;
     let options = PictToRasterOptions {
         width,
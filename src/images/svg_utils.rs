@@ -117,6 +117,63 @@ pub fn write_xml_escaped(buf: &mut String, text: &str) {
     }
 }
 
+/// Write an SVG `<image>` element embedding PNG data as a base64 data URL.
+///
+/// The image is stretched to fill the rectangle, matching GDI blit semantics.
+pub fn write_png_image(buf: &mut String, x: f64, y: f64, width: f64, height: f64, png: &[u8]) {
+    use base64::Engine;
+
+    buf.push_str("<image x=\"");
+    write_num(buf, x);
+    buf.push_str("\" y=\"");
+    write_num(buf, y);
+    buf.push_str("\" width=\"");
+    write_num(buf, width);
+    buf.push_str("\" height=\"");
+    write_num(buf, height);
+    buf.push_str("\" preserveAspectRatio=\"none\" href=\"data:image/png;base64,");
+    base64::engine::general_purpose::STANDARD.encode_string(png, buf);
+    buf.push_str("\"/>");
+}
+
+/// Build an SVG `<image>` element for a bitmap stretched between two corners.
+///
+/// The corners may come in any order: a destination whose right edge lies
+/// left of its left edge (or bottom above top) mirrors the bitmap, as GDI
+/// does for negative extents. Returns `None` for an empty destination or if
+/// PNG encoding fails.
+pub fn bitmap_image_element(
+    from: (f64, f64),
+    to: (f64, f64),
+    image: &image::RgbaImage,
+) -> Option<String> {
+    use image::imageops::{flip_horizontal, flip_vertical};
+
+    let (width, height) = ((to.0 - from.0).abs(), (to.1 - from.1).abs());
+    if width == 0.0 || height == 0.0 {
+        return None;
+    }
+
+    let png = match (to.0 < from.0, to.1 < from.1) {
+        (false, false) => crate::images::dib::encode_png(image),
+        (true, false) => crate::images::dib::encode_png(&flip_horizontal(image)),
+        (false, true) => crate::images::dib::encode_png(&flip_vertical(image)),
+        (true, true) => crate::images::dib::encode_png(&flip_vertical(&flip_horizontal(image))),
+    }
+    .ok()?;
+
+    let mut s = String::with_capacity(128 + png.len() * 4 / 3);
+    write_png_image(
+        &mut s,
+        from.0.min(to.0),
+        from.1.min(to.1),
+        width,
+        height,
+        &png,
+    );
+    Some(s)
+}
+
 /// Write SVG stroke attributes for a pen directly to a buffer.
 ///
 /// # Arguments
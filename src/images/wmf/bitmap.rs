@@ -0,0 +1,152 @@
+//! Embedded raster records
+//!
+//! Parses the WMF records that carry a device independent bitmap and
+//! describes where it is drawn:
+//!
+//! - `META_STRETCHDIB` (0x0F43)
+//! - `META_DIBSTRETCHBLT` (0x0B41)
+//! - `META_DIBBITBLT` (0x0940)
+//!
+//! The blit variants without a bitmap only paint with the current brush
+//! and are not handled here.
+//!
+//! # References
+//!
+//! - [MS-WMF] 2.3.1 Bitmap Record Types
+
+use super::constants::record;
+use super::parser::WmfRecord;
+use crate::common::binary::{read_i16_le, read_u16_le};
+use crate::images::dib::{Dib, rop_uses_source};
+use image::RgbaImage;
+
+/// `DIB_PAL_COLORS`: the color table holds indices into the logical palette
+const DIB_PAL_COLORS: u16 = 1;
+
+/// A bitmap drawn into a destination rectangle
+#[derive(Debug, Clone)]
+pub(crate) struct DibBlit<'a> {
+    /// Destination rectangle in logical units (x, y, width, height)
+    pub dest: (i16, i16, i16, i16),
+    /// Source rectangle in bitmap pixels (x, y, width, height)
+    pub src: (i16, i16, i16, i16),
+    /// Whether `src` is in DIB coordinates (bottom-left origin for
+    /// bottom-up bitmaps) rather than having a top-left origin
+    dib_origin: bool,
+    /// Packed DIB
+    dib: &'a [u8],
+}
+
+impl<'a> DibBlit<'a> {
+    /// Parse a bitmap record, returning `None` for other records and for
+    /// blits that do not copy a bitmap.
+    pub fn parse(rec: &'a WmfRecord) -> Option<Self> {
+        let p = &rec.params[..];
+        let word = |offset: usize| read_i16_le(p, offset).unwrap_or(0);
+        let rop = u32::from_le_bytes(p.get(0..4)?.try_into().ok()?);
+
+        let (dest, src, dib_origin, dib_offset) = match rec.function {
+            record::STRETCH_DIB => {
+                if read_u16_le(p, 4).ok()? == DIB_PAL_COLORS {
+                    return None;
+                }
+                (
+                    (word(20), word(18), word(16), word(14)),
+                    (word(12), word(10), word(8), word(6)),
+                    true,
+                    22,
+                )
+            },
+            record::DIB_STRETCH_BLT => (
+                (word(18), word(16), word(14), word(12)),
+                (word(10), word(8), word(6), word(4)),
+                false,
+                20,
+            ),
+            record::DIB_BIT_BLT => (
+                (word(14), word(12), word(10), word(8)),
+                (word(6), word(4), word(10), word(8)),
+                false,
+                16,
+            ),
+            _ => return None,
+        };
+
+        // The shortest DIB header is 12 bytes; anything less is a blit
+        // without a bitmap.
+        let dib = p.get(dib_offset..).filter(|dib| dib.len() >= 12)?;
+        if !rop_uses_source(rop) {
+            return None;
+        }
+        Some(Self {
+            dest,
+            src,
+            dib_origin,
+            dib,
+        })
+    }
+
+    /// Decode the bitmap and cut out the source rectangle
+    pub fn image(&self) -> Option<RgbaImage> {
+        let dib = Dib::parse(self.dib).ok()?;
+        let (x, y, w, h) = self.src;
+        if w <= 0 || h <= 0 {
+            return Some(dib.into_image());
+        }
+        let (x, y, w, h) = (x as i32, y as i32, w as i32, h as i32);
+        if self.dib_origin {
+            dib.dib_region(x, y, w, h)
+        } else {
+            dib.region(x, y, w, h)
+        }
+    }
+
+    /// Destination corners `((left, top), (right, bottom))` in logical
+    /// units; mirrored blits have `right < left` or `bottom < top`.
+    pub fn dest_corners(&self) -> ((i16, i16), (i16, i16)) {
+        let (x, y, w, h) = self.dest;
+        ((x, y), (x.saturating_add(w), y.saturating_add(h)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::images::wmf::{WmfParser, convert_wmf_to_svg};
+    use std::path::PathBuf;
+
+    fn fixture() -> Vec<u8> {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/images/wmf/dib-8bit-24bit.wmf");
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn test_parse_bitmap_records() {
+        let parser = WmfParser::new(&fixture()).unwrap();
+        let blits: Vec<_> = parser.records.iter().filter_map(DibBlit::parse).collect();
+        assert_eq!(blits.len(), 2);
+
+        // META_STRETCHDIB with an RLE8-compressed 8-bit DIB
+        assert_eq!(blits[0].dest, (0, 0, 100, 100));
+        let image = blits[0].image().unwrap();
+        assert_eq!(image.dimensions(), (4, 4));
+        assert_eq!(image.get_pixel(0, 0).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(3, 3).0, [255, 255, 0, 255]);
+
+        // META_DIBSTRETCHBLT with a bottom-up 24-bit DIB
+        assert_eq!(blits[1].dest_corners(), ((100, 0), (200, 100)));
+        let image = blits[1].image().unwrap();
+        assert_eq!(image.get_pixel(0, 0).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(0, 3).0, [0, 0, 255, 255]);
+    }
+
+    #[test]
+    fn test_svg_embeds_bitmaps() {
+        let svg = convert_wmf_to_svg(&fixture()).unwrap();
+        assert_eq!(svg.matches("<image ").count(), 2);
+        assert!(svg.contains("href=\"data:image/png;base64,"));
+        // The fixture is 200x100 logical units, scaled to fit 768x512
+        assert!(svg.contains("<image x=\"384\" y=\"0\" width=\"384\" height=\"384\""));
+    }
+}
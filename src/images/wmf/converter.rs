@@ -3,10 +3,13 @@
 // Converts WMF metafiles to modern raster formats (PNG, JPEG, WebP).
 //
 // Similar to EMF, full WMF rendering requires implementing a complete GDI rendering engine.
-// This implementation provides extraction and placeholder generation.
+// This implementation composites embedded bitmaps and otherwise generates a placeholder.
 
+use super::bitmap::DibBlit;
 use super::parser::WmfParser;
+use super::svg::{BoundsCalculator, CoordinateTransform};
 use crate::common::error::{Error, Result};
+use crate::images::dib::draw_scaled;
 use image::{DynamicImage, ImageBuffer, ImageFormat, Rgba, RgbaImage};
 use std::io::Cursor;
 
@@ -76,59 +79,42 @@ impl WmfConverter {
         }
     }
 
-    /// Try to extract embedded bitmap from WMF records
+    /// Render the embedded bitmaps of the WMF onto a canvas
     ///
     /// WMF files can contain embedded bitmaps via:
     /// - META_DIBSTRETCHBLT (0x0B41)
     /// - META_STRETCHDIB (0x0F43)
     /// - META_DIBBITBLT (0x0940)
-    fn extract_embedded_bitmap(&self) -> Option<DynamicImage> {
-        for record in &self.parser.records {
-            match record.function {
-                0x0B41 | 0x0F43 | 0x0940 => {
-                    // These records may contain DIB data
-                    if let Some(img) = self.parse_dib_from_record(&record.params) {
-                        return Some(img);
-                    }
-                },
-                _ => {},
-            }
-        }
-        None
-    }
-
-    /// Parse DIB data from WMF record parameters
-    fn parse_dib_from_record(&self, data: &[u8]) -> Option<DynamicImage> {
-        if data.len() < 40 {
+    ///
+    /// Each bitmap is scaled from its logical destination rectangle to the
+    /// output size. Returns `None` if the WMF contains no decodable bitmap.
+    fn render_bitmaps(&self, width: u32, height: u32) -> Option<RgbaImage> {
+        let blits: Vec<_> = self
+            .parser
+            .records
+            .iter()
+            .filter_map(DibBlit::parse)
+            .filter_map(|blit| blit.image().map(|image| (blit, image)))
+            .collect();
+        if blits.is_empty() {
             return None;
         }
 
-        // Try to construct BMP from DIB
-        if let Ok(img) = self.construct_bmp_from_dib(data) {
-            return Some(img);
-        }
-
-        None
-    }
-
-    /// Construct a BMP image from DIB data
-    fn construct_bmp_from_dib(&self, dib_data: &[u8]) -> Result<DynamicImage> {
-        let file_size = 14u32 + dib_data.len() as u32;
-        let pixel_data_offset = 14u32 + 40u32;
-
-        let mut bmp_data = Vec::with_capacity(file_size as usize);
-
-        // BMP file header
-        bmp_data.extend_from_slice(b"BM");
-        bmp_data.extend_from_slice(&file_size.to_le_bytes());
-        bmp_data.extend_from_slice(&[0u8; 4]);
-        bmp_data.extend_from_slice(&pixel_data_offset.to_le_bytes());
-        bmp_data.extend_from_slice(dib_data);
-
-        match image::load_from_memory(&bmp_data) {
-            Ok(img) => Ok(img),
-            Err(_) => Err(Error::ParseError("Failed to load DIB as BMP".into())),
+        let bbox = if let Some(ref p) = self.parser.placeable {
+            (p.left, p.top, p.right, p.bottom)
+        } else {
+            BoundsCalculator::scan_records(&self.parser.records)
+        };
+        let transform = CoordinateTransform::new(bbox, width as f64, height as f64);
+
+        let mut canvas = ImageBuffer::from_pixel(width, height, self.options.background_color);
+        for (blit, image) in &blits {
+            let ((left, top), (right, bottom)) = blit.dest_corners();
+            let (x1, y1) = transform.point(left, top);
+            let (x2, y2) = transform.point(right, bottom);
+            draw_scaled(&mut canvas, image, x1, y1, x2 - x1, y2 - y1);
         }
+        Some(canvas)
     }
 
     /// Create a placeholder image
@@ -167,17 +153,8 @@ impl WmfConverter {
     pub fn convert_to_image(&self) -> Result<DynamicImage> {
         let (target_width, target_height) = self.calculate_dimensions();
 
-        // Try to extract embedded bitmap first
-        if let Some(embedded) = self.extract_embedded_bitmap() {
-            if embedded.width() != target_width || embedded.height() != target_height {
-                return Ok(DynamicImage::ImageRgba8(image::imageops::resize(
-                    &embedded,
-                    target_width,
-                    target_height,
-                    image::imageops::FilterType::Lanczos3,
-                )));
-            }
-            return Ok(embedded);
+        if let Some(canvas) = self.render_bitmaps(target_width, target_height) {
+            return Ok(DynamicImage::ImageRgba8(canvas));
         }
 
         // Create placeholder
@@ -212,3 +189,26 @@ impl WmfConverter {
         self.convert_to_format(ImageFormat::WebP)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_render_embedded_bitmaps() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/images/wmf/dib-8bit-24bit.wmf");
+        let parser = WmfParser::new(&std::fs::read(path).unwrap()).unwrap();
+        let converter = WmfConverter::new(parser, WmfToRasterOptions::default());
+
+        let image = converter.convert_to_image().unwrap().to_rgba8();
+        assert_eq!(image.dimensions(), (200, 100));
+        // 8-bit DIB on the left: green and yellow halves
+        assert_eq!(image.get_pixel(25, 50).0, [0, 255, 0, 255]);
+        assert_eq!(image.get_pixel(75, 50).0, [255, 255, 0, 255]);
+        // 24-bit DIB on the right: red over blue
+        assert_eq!(image.get_pixel(150, 25).0, [255, 0, 0, 255]);
+        assert_eq!(image.get_pixel(150, 75).0, [0, 0, 255, 255]);
+    }
+}
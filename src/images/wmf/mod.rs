@@ -10,6 +10,7 @@
 // - [MS-WMF]: Windows Metafile Format Specification
 // - https://learn.microsoft.com/en-us/openspecs/windows_protocols/ms-wmf/
 
+mod bitmap;
 mod constants;
 pub mod converter;
 pub mod parser;
@@ -22,6 +23,7 @@ pub use parser::WmfParser;
 pub use svg::WmfConverter as WmfSvgConverter;
 
 use crate::common::error::Result;
+use crate::common::trace;
 use image::ImageFormat;
 
 /// Convert WMF data to a raster image in the specified format
@@ -51,6 +53,13 @@ pub fn convert_wmf(
     width: Option<u32>,
     height: Option<u32>,
 ) -> Result<Vec<u8>> {
+    let _span = trace::debug_span!(
+        "convert_image",
+        source = "wmf",
+        target = ?format,
+        size = wmf_data.len()
+    )
+    .entered();
     let parser = WmfParser::new(wmf_data)?;
     let options = WmfToRasterOptions {
         width,
@@ -12,6 +12,7 @@
 //! - **aarch64**: NEON
 //! - **Other**: Scalar fallback
 
+use super::super::bitmap::DibBlit;
 use super::super::constants::record;
 use super::super::parser::WmfRecord;
 use crate::common::binary::{read_i16_le, read_u16_le};
@@ -101,6 +102,13 @@ impl BoundsCalculator {
                         );
                     }
                 },
+                record::STRETCH_DIB | record::DIB_STRETCH_BLT | record::DIB_BIT_BLT => {
+                    if let Some(blit) = DibBlit::parse(rec) {
+                        let ((l, t), (r, b)) = blit.dest_corners();
+                        Self::update(&mut left, &mut top, &mut right, &mut bottom, l, t);
+                        Self::update(&mut left, &mut top, &mut right, &mut bottom, r, b);
+                    }
+                },
                 record::EXT_TEXT_OUT if rec.params.len() >= 8 => {
                     let y = read_i16_le(&rec.params, 0).unwrap_or(0);
                     let x = read_i16_le(&rec.params, 2).unwrap_or(0);
@@ -61,6 +61,11 @@
 //! - XML entity escaping
 //! - Font attribute application
 //!
+//! ## Embedded Bitmaps
+//! - META_STRETCHDIB, META_DIBSTRETCHBLT and META_DIBBITBLT
+//! - DIBs decoded to `<image>` elements with base64 PNG data URLs
+//! - Source rectangles cropped, negative destination extents mirrored
+
 //! # Architecture
 //!
 //! - `bounds`: Calculates bounding boxes from WMF records
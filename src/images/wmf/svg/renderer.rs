@@ -4,13 +4,14 @@
 //! minimal SVG elements for drawing operations. Matches libwmf behavior while
 //! producing compact, optimized SVG output.
 
+use super::super::bitmap::DibBlit;
 use super::super::constants::record;
 use super::super::parser::WmfRecord;
 use super::state::{Brush, Font, GdiObject, GraphicsState, Pen};
 use super::style::{fill_attr, map_font_family, stroke_attrs};
 use super::transform::CoordinateTransform;
 use crate::common::binary::{read_i16_le, read_u16_le};
-use crate::images::svg_utils::{write_color_hex, write_num};
+use crate::images::svg_utils::{bitmap_image_element, write_color_hex, write_num};
 
 /// Type of arc rendering
 #[derive(Debug, Clone, Copy)]
@@ -53,6 +54,9 @@ impl SvgRenderer {
             record::CHORD => self.render_chord(rec),
             record::ROUND_RECT => self.render_round_rect(rec),
             record::POLYPOLYGON => self.render_polypolygon(rec),
+            record::STRETCH_DIB | record::DIB_STRETCH_BLT | record::DIB_BIT_BLT => {
+                self.render_bitmap(rec)
+            },
             _ => None,
         }
     }
@@ -681,4 +685,16 @@ impl SvgRenderer {
 
         Some(s)
     }
+
+    fn render_bitmap(&self, rec: &WmfRecord) -> Option<String> {
+        let blit = DibBlit::parse(rec)?;
+        let image = blit.image()?;
+        let ((left, top), (right, bottom)) = blit.dest_corners();
+
+        bitmap_image_element(
+            self.transform.point(left, top),
+            self.transform.point(right, bottom),
+            &image,
+        )
+    }
 }
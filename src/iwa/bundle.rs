@@ -13,6 +13,7 @@ use std::path::{Path, PathBuf};
 use plist::Value;
 use soapberry_zip::office::ArchiveReader;
 
+use crate::common::trace;
 use crate::iwa::archive::{Archive, ArchiveObject};
 use crate::iwa::zip_utils::parse_iwa_files_from_archive;
 use crate::iwa::{Error, Result};
@@ -350,9 +351,9 @@ impl Bundle {
 
         // Verify each archive has at least one object
         let mut total_objects = 0;
-        for (archive_name, archive) in &self.archives {
+        for (_archive_name, archive) in &self.archives {
             if archive.objects.is_empty() {
-                eprintln!("Warning: Archive '{}' contains no objects", archive_name);
+                trace::warn!(archive = %_archive_name, "archive contains no objects");
             }
             total_objects += archive.objects.len();
         }
@@ -4,6 +4,7 @@
 
 use std::path::Path;
 
+use super::shape_extractor::SlideShapeExtractor;
 use super::show::KeynoteShow;
 use super::slide::KeynoteSlide;
 use crate::iwa::Result;
@@ -116,6 +117,7 @@ impl KeynoteDocument {
     /// ```
     pub fn slides(&self) -> Result<Vec<KeynoteSlide>> {
         let mut slides = Vec::new();
+        let shape_extractor = SlideShapeExtractor::new(&self.bundle, &self.object_index);
 
         // Find slide archives (message type 5/6 is KN.SlideArchive, type 1102 in our decoder)
         let slide_objects = self.bundle.find_objects_by_type(1102);
@@ -130,14 +132,14 @@ impl KeynoteDocument {
                 .chain(alt_slide_objects_6.iter())
                 .enumerate()
             {
-                let slide = self.parse_slide(index, object)?;
+                let slide = self.parse_slide(index, object, &shape_extractor)?;
                 if !slide.is_empty() {
                     slides.push(slide);
                 }
             }
         } else {
             for (index, (_archive_name, object)) in slide_objects.iter().enumerate() {
-                let slide = self.parse_slide(index, object)?;
+                let slide = self.parse_slide(index, object, &shape_extractor)?;
                 if !slide.is_empty() {
                     slides.push(slide);
                 }
@@ -171,6 +173,7 @@ impl KeynoteDocument {
         &self,
         index: usize,
         object: &crate::iwa::archive::ArchiveObject,
+        shape_extractor: &SlideShapeExtractor<'_>,
     ) -> Result<KeynoteSlide> {
         use prost::Message;
 
@@ -233,10 +236,16 @@ impl KeynoteDocument {
                 }
 
                 // Extract speaker notes
-                if let Some(ref note_ref) = slide_archive.note
-                    && let Ok(notes) = self.extract_speaker_notes(note_ref.identifier)
-                {
-                    slide.notes = Some(notes);
+                if let Ok(notes) = shape_extractor.presenter_notes(&slide_archive) {
+                    slide.notes = notes;
+                }
+
+                // Extract shapes with their geometry and the layout name
+                if let Ok(shapes) = shape_extractor.shapes(&slide_archive) {
+                    slide.shapes = shapes;
+                }
+                if let Ok(layout_name) = shape_extractor.layout_name(&slide_archive) {
+                    slide.layout_name = layout_name;
                 }
             }
         }
@@ -353,37 +362,6 @@ impl KeynoteDocument {
         Ok(String::new())
     }
 
-    /// Extract speaker notes from a NoteArchive object
-    fn extract_speaker_notes(&self, note_id: u64) -> Result<String> {
-        use prost::Message;
-
-        if let Some(resolved) = self.object_index.resolve_object(&self.bundle, note_id)? {
-            for msg in &resolved.messages {
-                if let Ok(note_archive) = crate::iwa::protobuf::kn::NoteArchive::decode(&*msg.data)
-                {
-                    // The note contains a reference to a TSWP.StorageArchive
-                    let storage_id = note_archive.contained_storage.identifier;
-                    if let Some(storage_obj) =
-                        self.object_index.resolve_object(&self.bundle, storage_id)?
-                    {
-                        for storage_msg in &storage_obj.messages {
-                            if let Ok(storage) = crate::iwa::protobuf::tswp::StorageArchive::decode(
-                                &*storage_msg.data,
-                            ) {
-                                let notes_text = storage.text.join("\n");
-                                if !notes_text.is_empty() {
-                                    return Ok(notes_text);
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-
-        Ok(String::new())
-    }
-
     /// Extract presentation metadata.
     ///
     /// Returns metadata from the Keynote bundle's Properties.plist file.
@@ -9,7 +9,7 @@
 //! - Master slide identification
 //! - Build animations and transitions
 //! - Speaker notes
-//! - Multimedia references
+//! - Shapes with geometry, text and media references
 //!
 //! ## Example
 //!
@@ -31,9 +31,13 @@
 //! ```
 
 pub mod document;
+pub mod shape;
+pub mod shape_extractor;
 pub mod show;
 pub mod slide;
 
 pub use document::KeynoteDocument;
+pub use shape::{ShapeGeometry, ShapeKind, SlideShape};
+pub use shape_extractor::SlideShapeExtractor;
 pub use show::KeynoteShow;
 pub use slide::{BuildAnimation, KeynoteSlide, SlideTransition};
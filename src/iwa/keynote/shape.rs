@@ -0,0 +1,153 @@
+//! Keynote Slide Shapes
+//!
+//! Shapes are the drawables placed on a slide: text boxes, images, tables,
+//! movies and plain vector shapes.
+
+use crate::iwa::protobuf::tsd;
+
+/// A drawable on a slide
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideShape {
+    /// Object identifier of the drawable
+    pub id: u64,
+    /// What the drawable is
+    pub kind: ShapeKind,
+    /// Position and size on the slide, in points
+    pub geometry: Option<ShapeGeometry>,
+    /// Text inside the shape, for text boxes and placeholders
+    pub text: Option<String>,
+    /// File name of the referenced media in the bundle's `Data` directory
+    ///
+    /// Pass it to `Document::extract_media` to read the image or movie.
+    pub media_filename: Option<String>,
+    /// Position of the shape in the slide's build order, if it is built in
+    pub build_order: Option<usize>,
+}
+
+impl SlideShape {
+    /// Create a shape of the given kind without geometry or content
+    pub fn new(id: u64, kind: ShapeKind) -> Self {
+        Self {
+            id,
+            kind,
+            geometry: None,
+            text: None,
+            media_filename: None,
+            build_order: None,
+        }
+    }
+
+    /// Check if the shape holds text
+    pub fn has_text(&self) -> bool {
+        self.text.as_deref().is_some_and(|text| !text.is_empty())
+    }
+}
+
+/// Kind of a slide drawable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeKind {
+    /// Text box or shape with text
+    TextBox,
+    /// Title, body or object placeholder from the slide layout
+    Placeholder,
+    /// Image, or an image placeholder without media
+    Image,
+    /// Table
+    Table,
+    /// Movie or audio
+    Media,
+    /// Vector shape without text
+    Shape,
+    /// Other/unknown drawable
+    Other,
+}
+
+impl ShapeKind {
+    /// Get a human-readable name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::TextBox => "Text Box",
+            Self::Placeholder => "Placeholder",
+            Self::Image => "Image",
+            Self::Table => "Table",
+            Self::Media => "Media",
+            Self::Shape => "Shape",
+            Self::Other => "Other",
+        }
+    }
+}
+
+/// Position, size and rotation of a drawable, in points
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeGeometry {
+    /// Left edge
+    pub x: f32,
+    /// Top edge
+    pub y: f32,
+    /// Width
+    pub width: f32,
+    /// Height
+    pub height: f32,
+    /// Rotation in degrees
+    pub angle: f32,
+}
+
+impl ShapeGeometry {
+    /// Convert a TSD.GeometryArchive
+    pub fn from_archive(geometry: &tsd::GeometryArchive) -> Self {
+        let (x, y) = geometry
+            .position
+            .as_ref()
+            .map_or((0.0, 0.0), |point| (point.x, point.y));
+        let (width, height) = geometry
+            .size
+            .as_ref()
+            .map_or((0.0, 0.0), |size| (size.width, size.height));
+
+        Self {
+            x,
+            y,
+            width,
+            height,
+            angle: geometry.angle.unwrap_or(0.0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iwa::protobuf::tsp;
+
+    #[test]
+    fn test_geometry_from_archive() {
+        let archive = tsd::GeometryArchive {
+            position: Some(tsp::Point { x: 10.0, y: 20.0 }),
+            size: Some(tsp::Size {
+                width: 300.0,
+                height: 150.0,
+            }),
+            angle: Some(45.0),
+            ..Default::default()
+        };
+        let geometry = ShapeGeometry::from_archive(&archive);
+        assert_eq!(geometry.x, 10.0);
+        assert_eq!(geometry.y, 20.0);
+        assert_eq!(geometry.width, 300.0);
+        assert_eq!(geometry.height, 150.0);
+        assert_eq!(geometry.angle, 45.0);
+
+        let empty = ShapeGeometry::from_archive(&tsd::GeometryArchive::default());
+        assert_eq!(empty.width, 0.0);
+        assert_eq!(empty.angle, 0.0);
+    }
+
+    #[test]
+    fn test_shape_text() {
+        let mut shape = SlideShape::new(7, ShapeKind::TextBox);
+        assert!(!shape.has_text());
+        shape.text = Some("Hello".to_string());
+        assert!(shape.has_text());
+        assert_eq!(shape.kind.name(), "Text Box");
+    }
+}
@@ -0,0 +1,288 @@
+//! Shape Extraction from Keynote Slides
+//!
+//! Resolves the drawables, placeholders, builds, notes and master of a
+//! KN.SlideArchive through the object index.
+//!
+//! ## Architecture
+//!
+//! - **SlideArchive**: References title/body placeholders, drawables, builds,
+//!   the presenter note and the master slide
+//! - **Drawables**: ShapeInfoArchive (text), ImageArchive, MovieArchive,
+//!   TableInfoArchive, GroupArchive (nested drawables) and plain shapes, each
+//!   carrying a DrawableArchive with its geometry
+//! - **PackageMetadata**: Maps the data references of images and movies to
+//!   file names in the bundle's `Data` directory
+
+use std::collections::HashMap;
+
+use super::shape::{ShapeGeometry, ShapeKind, SlideShape};
+use crate::iwa::Result;
+use crate::iwa::bundle::Bundle;
+use crate::iwa::object_index::ObjectIndex;
+use crate::iwa::protobuf::{kn, tsd, tsp, tst, tswp};
+use prost::Message;
+
+/// Message types of slide archives, including master slides
+const SLIDE_TYPES: [u32; 3] = [5, 6, 1102];
+
+/// Maximum nesting of groups that is followed
+const MAX_GROUP_DEPTH: usize = 16;
+
+/// Extractor for the shapes, notes and layout of Keynote slides
+pub struct SlideShapeExtractor<'a> {
+    bundle: &'a Bundle,
+    object_index: &'a ObjectIndex,
+    /// Data identifier -> file name in the `Data` directory
+    data_files: HashMap<u64, String>,
+}
+
+impl<'a> SlideShapeExtractor<'a> {
+    /// Create a new slide shape extractor
+    pub fn new(bundle: &'a Bundle, object_index: &'a ObjectIndex) -> Self {
+        Self {
+            bundle,
+            object_index,
+            data_files: Self::load_data_files(bundle),
+        }
+    }
+
+    /// Read the data file names from TSP.PackageMetadata (message type 11006)
+    fn load_data_files(bundle: &Bundle) -> HashMap<u64, String> {
+        let mut data_files = HashMap::new();
+
+        if let Some(archive) = bundle.get_archive("Index/Metadata.iwa") {
+            for object in &archive.objects {
+                for msg in &object.messages {
+                    if msg.type_ == 11006
+                        && let Ok(metadata) = tsp::PackageMetadata::decode(&*msg.data)
+                    {
+                        for data in metadata.datas {
+                            let name = data.file_name.unwrap_or(data.preferred_file_name);
+                            data_files.insert(data.identifier, name);
+                        }
+                    }
+                }
+            }
+        }
+
+        data_files
+    }
+
+    /// Extract the shapes of a slide: placeholders first, then drawables in
+    /// z-order, with groups flattened
+    pub fn shapes(&self, slide: &kn::SlideArchive) -> Result<Vec<SlideShape>> {
+        let build_order = self.build_order(slide)?;
+        let mut shapes = Vec::new();
+
+        let placeholders = [
+            &slide.title_placeholder,
+            &slide.body_placeholder,
+            &slide.object_placeholder,
+        ];
+        for reference in placeholders.into_iter().flatten() {
+            self.collect_shapes(reference.identifier, 0, &mut shapes)?;
+        }
+        for reference in &slide.drawables {
+            self.collect_shapes(reference.identifier, 0, &mut shapes)?;
+        }
+
+        for shape in &mut shapes {
+            shape.build_order = build_order.get(&shape.id).copied();
+        }
+
+        Ok(shapes)
+    }
+
+    /// Extract the presenter notes of a slide
+    pub fn presenter_notes(&self, slide: &kn::SlideArchive) -> Result<Option<String>> {
+        let Some(note_ref) = &slide.note else {
+            return Ok(None);
+        };
+
+        if let Some(resolved) = self
+            .object_index
+            .resolve_object(self.bundle, note_ref.identifier)?
+        {
+            for msg in &resolved.messages {
+                if let Ok(note_archive) = kn::NoteArchive::decode(&*msg.data) {
+                    // The note contains a reference to a TSWP.StorageArchive
+                    return self.storage_text(note_archive.contained_storage.identifier);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the name of the master slide (layout) a slide is based on
+    pub fn layout_name(&self, slide: &kn::SlideArchive) -> Result<Option<String>> {
+        let Some(master_ref) = &slide.master else {
+            return Ok(None);
+        };
+
+        if let Some(resolved) = self
+            .object_index
+            .resolve_object(self.bundle, master_ref.identifier)?
+        {
+            for msg in &resolved.messages {
+                if SLIDE_TYPES.contains(&msg.type_)
+                    && let Ok(master) = kn::SlideArchive::decode(&*msg.data)
+                {
+                    return Ok(master.name.filter(|name| !name.is_empty()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Map each built drawable to its position in the slide's build order
+    fn build_order(&self, slide: &kn::SlideArchive) -> Result<HashMap<u64, usize>> {
+        let mut order = HashMap::new();
+
+        for build_ref in &slide.builds {
+            if let Some(resolved) = self
+                .object_index
+                .resolve_object(self.bundle, build_ref.identifier)?
+                && let Some(build) = resolved
+                    .messages
+                    .iter()
+                    .find_map(|msg| kn::BuildArchive::decode(&*msg.data).ok())
+            {
+                // A drawable built in several steps keeps its first position
+                let next = order.len();
+                order.entry(build.drawable.identifier).or_insert(next);
+            }
+        }
+
+        Ok(order)
+    }
+
+    /// Resolve a drawable and append its shapes, descending into groups
+    fn collect_shapes(
+        &self,
+        drawable_id: u64,
+        depth: usize,
+        shapes: &mut Vec<SlideShape>,
+    ) -> Result<()> {
+        let Some(resolved) = self.object_index.resolve_object(self.bundle, drawable_id)? else {
+            return Ok(());
+        };
+        let Some(msg) = resolved.messages.first() else {
+            return Ok(());
+        };
+
+        match msg.type_ {
+            // KN.PlaceholderArchive
+            7 => {
+                if let Ok(placeholder) = kn::PlaceholderArchive::decode(&*msg.data) {
+                    shapes.push(self.text_shape(
+                        drawable_id,
+                        ShapeKind::Placeholder,
+                        &placeholder.super_,
+                    )?);
+                }
+            },
+            // TSWP.ShapeInfoArchive
+            2011 => {
+                if let Ok(shape_info) = tswp::ShapeInfoArchive::decode(&*msg.data) {
+                    shapes.push(self.text_shape(drawable_id, ShapeKind::TextBox, &shape_info)?);
+                }
+            },
+            // TSD.ShapeArchive
+            3004 => {
+                if let Ok(shape) = tsd::ShapeArchive::decode(&*msg.data) {
+                    shapes.push(Self::drawable_shape(
+                        drawable_id,
+                        ShapeKind::Shape,
+                        &shape.super_,
+                    ));
+                }
+            },
+            // TSD.ImageArchive
+            3005 => {
+                if let Ok(image) = tsd::ImageArchive::decode(&*msg.data) {
+                    let mut shape =
+                        Self::drawable_shape(drawable_id, ShapeKind::Image, &image.super_);
+                    shape.media_filename = image
+                        .data
+                        .as_ref()
+                        .and_then(|data| self.data_files.get(&data.identifier).cloned());
+                    shapes.push(shape);
+                }
+            },
+            // TSD.MovieArchive
+            3007 => {
+                if let Ok(movie) = tsd::MovieArchive::decode(&*msg.data) {
+                    let mut shape =
+                        Self::drawable_shape(drawable_id, ShapeKind::Media, &movie.super_);
+                    shape.media_filename = movie
+                        .movie_data
+                        .as_ref()
+                        .and_then(|data| self.data_files.get(&data.identifier).cloned());
+                    shapes.push(shape);
+                }
+            },
+            // TSD.GroupArchive
+            3008 => {
+                if let Ok(group) = tsd::GroupArchive::decode(&*msg.data)
+                    && depth < MAX_GROUP_DEPTH
+                {
+                    for child in &group.children {
+                        self.collect_shapes(child.identifier, depth + 1, shapes)?;
+                    }
+                }
+            },
+            // TST.TableInfoArchive
+            6000 => {
+                if let Ok(table) = tst::TableInfoArchive::decode(&*msg.data) {
+                    shapes.push(Self::drawable_shape(
+                        drawable_id,
+                        ShapeKind::Table,
+                        &table.super_,
+                    ));
+                }
+            },
+            _ => shapes.push(SlideShape::new(drawable_id, ShapeKind::Other)),
+        }
+
+        Ok(())
+    }
+
+    /// Build a shape from the common drawable properties
+    fn drawable_shape(id: u64, kind: ShapeKind, drawable: &tsd::DrawableArchive) -> SlideShape {
+        let mut shape = SlideShape::new(id, kind);
+        shape.geometry = drawable.geometry.as_ref().map(ShapeGeometry::from_archive);
+        shape
+    }
+
+    /// Build a shape with the text of its contained storage
+    fn text_shape(
+        &self,
+        id: u64,
+        kind: ShapeKind,
+        shape_info: &tswp::ShapeInfoArchive,
+    ) -> Result<SlideShape> {
+        let mut shape = Self::drawable_shape(id, kind, &shape_info.super_.super_);
+        if let Some(storage) = &shape_info.contained_storage {
+            shape.text = self.storage_text(storage.identifier)?;
+        }
+        Ok(shape)
+    }
+
+    /// Get the text of a TSWP.StorageArchive, one line per paragraph
+    fn storage_text(&self, storage_id: u64) -> Result<Option<String>> {
+        if let Some(resolved) = self.object_index.resolve_object(self.bundle, storage_id)? {
+            for msg in &resolved.messages {
+                if (2001..=2022).contains(&msg.type_)
+                    && let Ok(storage) = tswp::StorageArchive::decode(&*msg.data)
+                {
+                    let text = storage.text.join("\n");
+                    return Ok((!text.is_empty()).then_some(text));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+}
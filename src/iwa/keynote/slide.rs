@@ -2,6 +2,7 @@
 //!
 //! Slides are the core content units in Keynote presentations.
 
+use super::shape::SlideShape;
 use crate::iwa::text::TextStorage;
 
 /// Represents a slide in a Keynote presentation
@@ -23,6 +24,10 @@ pub struct KeynoteSlide {
     pub transition: Option<SlideTransition>,
     /// Master slide reference
     pub master_slide_id: Option<u64>,
+    /// Name of the master slide (layout), e.g. "Title & Bullets"
+    pub layout_name: Option<String>,
+    /// Placeholders and drawables on the slide
+    pub shapes: Vec<SlideShape>,
 }
 
 impl KeynoteSlide {
@@ -37,6 +42,8 @@ impl KeynoteSlide {
             builds: Vec::new(),
             transition: None,
             master_slide_id: None,
+            layout_name: None,
+            shapes: Vec::new(),
         }
     }
 
@@ -73,6 +80,7 @@ impl KeynoteSlide {
             && self.text_content.is_empty()
             && self.notes.is_none()
             && self.text_storages.is_empty()
+            && self.shapes.is_empty()
     }
 
     /// Get number of build animations
@@ -162,7 +162,7 @@ pub use document::Document;
 pub use media::{MediaAsset, MediaManager, MediaStats, MediaType};
 pub use ref_graph::ReferenceGraph;
 pub use snappy::SnappyStream;
-pub use structured::{CellValue, Section, Slide, StructuredData, Table};
+pub use structured::{CellValue, NumberFormat, Section, Slide, StructuredData, Table};
 pub use text::{ParagraphStyle, TextExtractor, TextFragment, TextStorage, TextStyle};
 pub use zip_utils::{
     FileStructureInfo, analyze_file_structure, extract_message_types_from_archive,
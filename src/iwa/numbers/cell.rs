@@ -2,6 +2,7 @@
 //!
 //! Numbers supports various cell types including text, numbers, dates, formulas, and more.
 
+use chrono::NaiveDateTime;
 use std::fmt;
 
 /// Represents a cell value in a Numbers table
@@ -14,11 +15,18 @@ pub enum CellValue {
     Text(String),
     /// Numeric value (integer or floating-point)
     Number(f64),
+    /// Numeric value displayed with a currency or percentage format
+    FormattedNumber {
+        /// Raw value, e.g. `0.25` for a cell showing "25%"
+        value: f64,
+        /// How Numbers displays the value
+        format: NumberFormat,
+    },
     /// Boolean value
     Boolean(bool),
-    /// Date value (stored as timestamp)
-    Date(String),
-    /// Duration/time value
+    /// Date and time value
+    Date(NaiveDateTime),
+    /// Duration value in seconds
     Duration(f64),
     /// Formula (stored as string representation)
     Formula(String),
@@ -37,7 +45,7 @@ impl CellValue {
         match self {
             CellValue::Empty => CellType::Empty,
             CellValue::Text(_) => CellType::Text,
-            CellValue::Number(_) => CellType::Number,
+            CellValue::Number(_) | CellValue::FormattedNumber { .. } => CellType::Number,
             CellValue::Boolean(_) => CellType::Boolean,
             CellValue::Date(_) => CellType::Date,
             CellValue::Duration(_) => CellType::Duration,
@@ -51,9 +59,11 @@ impl CellValue {
         match self {
             CellValue::Empty => String::new(),
             CellValue::Text(s) => s.clone(),
-            CellValue::Number(n) => format!("{}", n),
+            CellValue::Number(n) | CellValue::FormattedNumber { value: n, .. } => {
+                format!("{}", n)
+            },
             CellValue::Boolean(b) => format!("{}", b),
-            CellValue::Date(d) => d.clone(),
+            CellValue::Date(d) => d.format("%Y-%m-%dT%H:%M:%S").to_string(),
             CellValue::Duration(d) => format!("{}", d),
             CellValue::Formula(f) => f.clone(),
             CellValue::Error(e) => format!("ERROR: {}", e),
@@ -63,7 +73,7 @@ impl CellValue {
     /// Try to get as a number
     pub fn as_number(&self) -> Option<f64> {
         match self {
-            CellValue::Number(n) => Some(*n),
+            CellValue::Number(n) | CellValue::FormattedNumber { value: n, .. } => Some(*n),
             CellValue::Duration(d) => Some(*d),
             CellValue::Text(s) => s.parse::<f64>().ok(),
             CellValue::Boolean(b) => Some(if *b { 1.0 } else { 0.0 }),
@@ -98,6 +108,15 @@ impl fmt::Display for CellValue {
     }
 }
 
+/// Display format hint of a numeric cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NumberFormat {
+    /// Currency with its ISO 4217 code, e.g. `"USD"`
+    Currency(String),
+    /// Percentage; the raw value is the fraction (0.25 for 25%)
+    Percentage,
+}
+
 /// Cell type enumeration
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellType {
@@ -175,4 +194,20 @@ mod tests {
         let bool_num = CellValue::Boolean(false);
         assert_eq!(bool_num.as_number(), Some(0.0));
     }
+
+    #[test]
+    fn test_typed_values() {
+        let price = CellValue::FormattedNumber {
+            value: 9.99,
+            format: NumberFormat::Currency("EUR".to_string()),
+        };
+        assert_eq!(price.cell_type(), CellType::Number);
+        assert_eq!(price.as_number(), Some(9.99));
+        assert_eq!(price.as_text(), "9.99");
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .and_then(|d| d.and_hms_opt(13, 30, 0))
+            .unwrap();
+        assert_eq!(CellValue::Date(date).as_text(), "2024-02-29T13:30:00");
+    }
 }
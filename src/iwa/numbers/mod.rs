@@ -35,7 +35,7 @@ pub mod sheet;
 pub mod table;
 pub mod table_extractor;
 
-pub use cell::{CellType, CellValue};
+pub use cell::{CellType, CellValue, NumberFormat};
 pub use document::NumbersDocument;
 pub use sheet::NumbersSheet;
 pub use table::NumbersTable;
@@ -16,6 +16,8 @@ pub struct NumbersTable {
     pub column_count: usize,
     /// Cell data indexed by (row, column)
     pub cells: HashMap<(usize, usize), CellValue>,
+    /// Formula text of formula cells, indexed by (row, column)
+    pub formulas: HashMap<(usize, usize), String>,
     /// Column headers (if present)
     pub column_headers: Vec<String>,
     /// Row headers (if present)
@@ -30,6 +32,7 @@ impl NumbersTable {
             row_count: 0,
             column_count: 0,
             cells: HashMap::new(),
+            formulas: HashMap::new(),
             column_headers: Vec::new(),
             row_headers: Vec::new(),
         }
@@ -47,6 +50,13 @@ impl NumbersTable {
         self.column_count = self.column_count.max(col + 1);
     }
 
+    /// Get the formula of a cell, such as `"=SUM(A1:A3)"`
+    ///
+    /// The cell value holds the last result Numbers computed for it.
+    pub fn get_formula(&self, row: usize, col: usize) -> Option<&str> {
+        self.formulas.get(&(row, col)).map(String::as_str)
+    }
+
     /// Get all cell values in a specific row
     pub fn get_row(&self, row: usize) -> Vec<CellValue> {
         (0..self.column_count)
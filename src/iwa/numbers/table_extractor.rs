@@ -9,7 +9,8 @@
 //! - **DataStore**: Contains references to various data tables (strings, formulas, styles)
 //! - **TableDataList**: Maps keys to actual cell content (strings, formulas, formats)
 //! - **TileStorage**: Contains the actual cells in a sparse tile-based structure
-//! - **Tile**: Contains rows of cells with their values
+//! - **Tile**: Contains rows of cells, each packed into a storage buffer with
+//!   per-cell flags announcing which values and list keys follow
 //!
 //! ## Example
 //!
@@ -29,12 +30,14 @@
 //! }
 //! ```
 
-use super::cell::CellValue;
+use super::cell::{CellValue, NumberFormat};
 use super::table::NumbersTable;
+use crate::iwa::Result;
 use crate::iwa::bundle::Bundle;
 use crate::iwa::object_index::{ObjectIndex, ResolvedObject};
-use crate::iwa::protobuf::{tsce, tst};
-use crate::iwa::{Error, Result};
+use crate::iwa::protobuf::tst::table_data_list::ListEntry;
+use crate::iwa::protobuf::{tsce, tsk, tst};
+use chrono::{NaiveDate, NaiveDateTime, TimeDelta};
 use prost::Message;
 use std::collections::HashMap;
 
@@ -108,29 +111,26 @@ impl<'a> TableDataExtractor<'a> {
         table.row_count = table_model.number_of_rows as usize;
         table.column_count = table_model.number_of_columns as usize;
 
-        // Extract string table for cell text values
-        // string_table is a required field, not Optional
-        let string_table =
-            self.load_table_data_list(table_model.data_store.string_table.identifier)?;
-
-        // Extract formula table for formula cells
-        // formula_table is a required field, not Optional
-        let formula_table =
-            self.load_table_data_list(table_model.data_store.formula_table.identifier)?;
+        // Cells refer to their strings, formulas and formats by key
+        let data_store = &table_model.data_store;
+        let lists = DataLists {
+            strings: self.load_table_data_list(data_store.string_table.identifier)?,
+            formulas: self.load_table_data_list(data_store.formula_table.identifier)?,
+            formats: self.load_table_data_list(data_store.format_table.identifier)?,
+            rich_text: match &data_store.rich_text_payload_table {
+                Some(reference) => self.load_table_data_list(reference.identifier)?,
+                None => HashMap::new(),
+            },
+        };
 
         // Parse tiles to extract cell data
-        self.parse_tiles(
-            &table_model.data_store.tiles,
-            &string_table,
-            &formula_table,
-            &mut table,
-        )?;
+        self.parse_tiles(&data_store.tiles, &lists, &mut table)?;
 
         Ok(table)
     }
 
-    /// Load a TableDataList from an object reference
-    fn load_table_data_list(&self, object_id: u64) -> Result<HashMap<u32, String>> {
+    /// Load the entries of a TableDataList from an object reference
+    fn load_table_data_list(&self, object_id: u64) -> Result<HashMap<u32, ListEntry>> {
         let mut result = HashMap::new();
 
         if let Some(resolved) = self.object_index.resolve_object(self.bundle, object_id)? {
@@ -140,9 +140,7 @@ impl<'a> TableDataExtractor<'a> {
                     && let Ok(data_list) = tst::TableDataList::decode(&*msg.data)
                 {
                     for entry in data_list.entries {
-                        if let Some(ref string_val) = entry.string {
-                            result.insert(entry.key, string_val.clone());
-                        }
+                        result.insert(entry.key, entry);
                     }
                 }
             }
@@ -155,20 +153,14 @@ impl<'a> TableDataExtractor<'a> {
     fn parse_tiles(
         &self,
         tile_storage: &tst::TileStorage,
-        string_table: &HashMap<u32, String>,
-        formula_table: &HashMap<u32, String>,
+        lists: &DataLists,
         table: &mut NumbersTable,
     ) -> Result<()> {
         // Resolve each tile reference and parse its contents
         for tile_ref in &tile_storage.tiles {
-            // tile is a required field, not Optional
-            let tile_reference = &tile_ref.tile;
-            self.parse_tile(
-                tile_reference.identifier,
-                string_table,
-                formula_table,
-                table,
-            )?;
+            // Each tile holds a band of rows
+            let first_row = tile_ref.tileid as usize * ROWS_PER_TILE;
+            self.parse_tile(tile_ref.tile.identifier, first_row, lists, table)?;
         }
 
         Ok(())
@@ -178,15 +170,17 @@ impl<'a> TableDataExtractor<'a> {
     fn parse_tile(
         &self,
         tile_id: u64,
-        string_table: &HashMap<u32, String>,
-        formula_table: &HashMap<u32, String>,
+        first_row: usize,
+        lists: &DataLists,
         table: &mut NumbersTable,
     ) -> Result<()> {
         if let Some(resolved) = self.object_index.resolve_object(self.bundle, tile_id)? {
             for msg in &resolved.messages {
                 // Tile messages are typically in the TST namespace
                 if let Ok(tile) = tst::Tile::decode(&*msg.data) {
-                    self.parse_tile_rows(&tile, string_table, formula_table, table)?;
+                    for row_info in &tile.row_infos {
+                        self.parse_tile_row(row_info, first_row, lists, table)?;
+                    }
                 }
             }
         }
@@ -194,46 +188,57 @@ impl<'a> TableDataExtractor<'a> {
         Ok(())
     }
 
-    /// Parse rows within a tile
-    fn parse_tile_rows(
-        &self,
-        tile: &tst::Tile,
-        string_table: &HashMap<u32, String>,
-        formula_table: &HashMap<u32, String>,
-        table: &mut NumbersTable,
-    ) -> Result<()> {
-        for row_info in &tile.row_infos {
-            self.parse_tile_row(row_info, string_table, formula_table, table)?;
-        }
-
-        Ok(())
-    }
-
     /// Parse a single tile row
     fn parse_tile_row(
         &self,
         row_info: &tst::TileRowInfo,
-        _string_table: &HashMap<u32, String>,
-        _formula_table: &HashMap<u32, String>,
+        first_row: usize,
+        lists: &DataLists,
         table: &mut NumbersTable,
     ) -> Result<()> {
-        let row_index = row_info.tile_row_index as usize;
-
-        // The cell_storage_buffer contains serialized Cell messages
-        // The cell_offsets buffer contains the byte offsets for each cell
-
-        // Parse cell offsets (variable-length encoded)
-        let offsets = self.parse_cell_offsets(&row_info.cell_offsets)?;
+        let row_index = first_row + row_info.tile_row_index as usize;
+
+        // Numbers 10 and later write the storage to new fields; older files
+        // use the original ones with the same layout
+        let (buffer, offsets, wide_offsets) = match (
+            &row_info.cell_storage_buffer_bnc,
+            &row_info.cell_offsets_bnc,
+        ) {
+            (Some(buffer), Some(offsets)) => (
+                buffer.as_slice(),
+                offsets.as_slice(),
+                row_info.has_wide_offsets(),
+            ),
+            _ => (
+                row_info.cell_storage_buffer.as_slice(),
+                row_info.cell_offsets.as_slice(),
+                false,
+            ),
+        };
 
-        // Parse each cell from the storage buffer
-        for (col_index, (offset, next_offset)) in
-            offsets.iter().zip(offsets.iter().skip(1)).enumerate()
+        for (col_index, offset) in Self::parse_cell_offsets(offsets, wide_offsets)
+            .into_iter()
+            .enumerate()
         {
-            let cell_data = &row_info.cell_storage_buffer[*offset..*next_offset];
+            let Some(cell) = offset
+                .and_then(|offset| buffer.get(offset..))
+                .and_then(StoredCell::parse)
+            else {
+                continue;
+            };
+
+            let value = self.parse_cell(&cell, lists)?;
+            if !value.is_empty() {
+                table.set_cell(row_index, col_index, value);
+            }
 
-            if let Ok(cell) = tst::Cell::decode(cell_data) {
-                let cell_value = self.parse_cell(&cell)?;
-                table.set_cell(row_index, col_index, cell_value);
+            if let Some(formula) = cell
+                .id(FLAG_FORMULA_ID)
+                .and_then(|id| lists.formulas.get(&id))
+                .and_then(|entry| entry.formula.as_ref())
+            {
+                let text = Self::extract_formula_string(formula, row_index, col_index);
+                table.formulas.insert((row_index, col_index), text);
             }
         }
 
@@ -242,221 +247,214 @@ impl<'a> TableDataExtractor<'a> {
 
     /// Parse cell offsets from the offsets buffer
     ///
-    /// Offsets are stored as variable-length integers (varints).
-    /// Each offset indicates the starting position of a cell in the storage buffer.
-    fn parse_cell_offsets(&self, offsets_buffer: &[u8]) -> Result<Vec<usize>> {
-        let mut offsets = vec![0]; // First cell always starts at offset 0
-        let mut pos = 0;
-
-        while pos < offsets_buffer.len() {
-            let (offset, bytes_read) = Self::decode_varint(&offsets_buffer[pos..])?;
-            pos += bytes_read;
-
-            // Offsets are cumulative
-            let prev_offset = *offsets.last().unwrap_or(&0);
-            offsets.push(prev_offset + offset);
-        }
-
-        Ok(offsets)
-    }
-
-    /// Decode a single varint from a byte slice
-    ///
-    /// Returns (value, bytes_consumed)
-    fn decode_varint(data: &[u8]) -> Result<(usize, usize)> {
-        let mut result: u64 = 0;
-        let mut shift = 0;
-        let mut bytes_read = 0;
-
-        for &byte in data.iter().take(10) {
-            // Max 10 bytes for u64
-            bytes_read += 1;
-            result |= u64::from(byte & 0x7F) << shift;
-
-            if byte & 0x80 == 0 {
-                return Ok((result as usize, bytes_read));
-            }
-
-            shift += 7;
-        }
-
-        Err(Error::ParseError("Invalid varint encoding".to_string()))
+    /// The buffer holds one little-endian 16-bit offset per column; a
+    /// negative offset marks a column without a cell. Wide offsets are
+    /// stored divided by four.
+    fn parse_cell_offsets(offsets_buffer: &[u8], wide_offsets: bool) -> Vec<Option<usize>> {
+        let scale = if wide_offsets { 4 } else { 1 };
+
+        offsets_buffer
+            .chunks_exact(2)
+            .map(|pair| {
+                let offset = i16::from_le_bytes([pair[0], pair[1]]);
+                (offset >= 0).then(|| offset as usize * scale)
+            })
+            .collect()
     }
 
-    /// Parse a single Cell protobuf message into a CellValue
-    fn parse_cell(&self, cell: &tst::Cell) -> Result<CellValue> {
-        use tst::CellValueType;
-
-        match cell.value_type() {
-            CellValueType::EmptyCellValueType => Ok(CellValue::Empty),
-
-            CellValueType::NumberCellValueType => {
-                let number = cell.number_value.unwrap_or(0.0);
-                Ok(CellValue::Number(number))
-            },
-
-            CellValueType::StringCellValueType => {
-                // String cells can store text directly or reference the string table
-                if let Some(ref string_val) = cell.string_value
-                    && !string_val.is_empty()
-                {
-                    Ok(CellValue::Text(string_val.clone()))
-                } else {
-                    // In some cases, strings are stored via references
-                    // The actual string data would be in the string_table
-                    // For a production implementation, we would:
-                    // 1. Check cell.text_style or cell.cell_style for a reference
-                    // 2. Resolve that reference to find the actual string value
-                    // 3. Look up the string in the string_table using a key
-                    //
-                    // For now, return Empty if no direct string value is present
-                    Ok(CellValue::Empty)
+    /// Convert a stored cell into a CellValue
+    fn parse_cell(&self, cell: &StoredCell<'_>, lists: &DataLists) -> Result<CellValue> {
+        let value = match cell.cell_type {
+            CELL_TYPE_NUMBER | CELL_TYPE_CURRENCY => {
+                let value = cell.number().unwrap_or(0.0);
+                let format = cell
+                    .id(FLAG_CURRENCY_FORMAT_ID)
+                    .or_else(|| cell.id(FLAG_NUMBER_FORMAT_ID))
+                    .and_then(|id| lists.formats.get(&id))
+                    .and_then(|entry| entry.format.as_ref())
+                    .and_then(number_format)
+                    .or_else(|| {
+                        // Currency cells without a stored format use the default currency
+                        (cell.cell_type == CELL_TYPE_CURRENCY)
+                            .then(|| NumberFormat::Currency(String::new()))
+                    });
+                match format {
+                    Some(format) => CellValue::FormattedNumber { value, format },
+                    None => CellValue::Number(value),
                 }
             },
 
-            CellValueType::BoolCellValueType => {
-                let bool_val = cell.bool_value.unwrap_or(false);
-                Ok(CellValue::Boolean(bool_val))
-            },
-
-            CellValueType::DateCellValueType => {
-                // Date values are stored as numbers (timestamp)
-                let date_num = cell.number_value.unwrap_or(0.0);
-                // Convert to string representation
-                // Note: Apple's date epoch is different from Unix epoch
-                Ok(CellValue::Date(format!("{}", date_num)))
-            },
-
-            CellValueType::DurationCellValueType => {
-                let duration = cell.number_value.unwrap_or(0.0);
-                Ok(CellValue::Duration(duration))
-            },
-
-            CellValueType::ErrorCellValueType => Ok(CellValue::Error("ERROR".to_string())),
-
-            CellValueType::ProvidedCellValueType => {
-                // Provided values may come from formulas or other sources
-                if let Some(ref formula) = cell.formula {
-                    // Extract formula string representation
-                    let formula_str = self.extract_formula_string(formula)?;
-                    Ok(CellValue::Formula(formula_str))
-                } else {
-                    Ok(CellValue::Empty)
+            CELL_TYPE_TEXT => cell
+                .id(FLAG_STRING_ID)
+                .and_then(|id| lists.strings.get(&id))
+                .and_then(|entry| entry.string.clone())
+                .map_or(CellValue::Empty, CellValue::Text),
+
+            CELL_TYPE_DATE => cell
+                .seconds()
+                .and_then(apple_date)
+                .map_or(CellValue::Empty, CellValue::Date),
+
+            CELL_TYPE_BOOL => CellValue::Boolean(cell.number().is_some_and(|n| n != 0.0)),
+
+            CELL_TYPE_DURATION => CellValue::Duration(cell.number().unwrap_or(0.0)),
+
+            CELL_TYPE_FORMULA_ERROR => CellValue::Error("ERROR".to_string()),
+
+            CELL_TYPE_RICH_TEXT => {
+                let payload = cell
+                    .id(FLAG_RICH_TEXT_ID)
+                    .and_then(|id| lists.rich_text.get(&id))
+                    .and_then(|entry| {
+                        entry
+                            .rich_text_payload
+                            .as_ref()
+                            .or(entry.reference.as_ref())
+                    });
+                match payload {
+                    Some(payload) => self
+                        .extract_rich_text_payload(payload.identifier)?
+                        .map_or(CellValue::Empty, CellValue::Text),
+                    None => CellValue::Empty,
                 }
             },
 
-            CellValueType::RichTextCellType => {
-                // Rich text requires resolving the richTextPayload reference
-                if let Some(ref payload_ref) = cell.rich_text_payload {
-                    if let Some(text) = self.extract_rich_text(payload_ref.identifier)? {
-                        Ok(CellValue::Text(text))
-                    } else {
-                        Ok(CellValue::Empty)
-                    }
-                } else {
-                    Ok(CellValue::Empty)
-                }
-            },
-        }
+            _ => CellValue::Empty,
+        };
+
+        Ok(value)
     }
 
-    /// Extract formula string from FormulaArchive
-    ///
-    ///   - Reconstructs formula text from Abstract Syntax Tree
-    ///   - Handles operators, functions, cell references, and constants
-    ///   - Based on TSCE.ASTNodeArrayArchive protobuf structure
-    ///   - Implements reverse-polish notation to infix conversion
+    /// Reconstruct formula text from a FormulaArchive
     ///
     /// iWork stores formulas as Abstract Syntax Trees (AST) in reverse-polish
-    /// notation (postfix). This function reconstructs the formula text by
-    /// traversing the AST and converting it to standard infix notation.
+    /// notation (postfix). This function rebuilds the infix text with a stack,
+    /// adding parentheses only where operator precedence requires them.
+    /// Relative references are resolved against the cell at `row`, `col`.
     ///
     /// # Performance
     ///
-    /// O(n) where n is the number of AST nodes. Uses a stack-based algorithm
-    /// for efficient conversion.
-    fn extract_formula_string(&self, formula: &tsce::FormulaArchive) -> Result<String> {
-        use crate::iwa::protobuf::tsce::ast_node_array_archive::AstNodeType;
-
-        let ast_array = &formula.ast_node_array;
-
-        // Formulas are stored in reverse-polish notation (postfix)
-        // We need to convert to infix notation using a stack
-        if ast_array.ast_node.is_empty() {
-            return Ok("=".to_string());
+    /// O(n) where n is the number of AST nodes.
+    fn extract_formula_string(formula: &tsce::FormulaArchive, row: usize, col: usize) -> String {
+        match Self::format_ast(&formula.ast_node_array, row, col) {
+            Some(text) => format!("={}", text),
+            None => "=".to_string(),
         }
+    }
+
+    /// Convert an AST node array to infix text
+    fn format_ast(ast_array: &tsce::AstNodeArrayArchive, row: usize, col: usize) -> Option<String> {
+        use crate::iwa::protobuf::tsce::ast_node_array_archive::AstNodeType;
 
-        // Stack to hold expression parts during reconstruction
-        let mut expr_stack: Vec<String> = Vec::new();
+        // Each entry is an expression and the precedence of its outermost operator
+        let mut stack: Vec<(String, u8)> = Vec::new();
 
-        // Process each AST node
         for node in &ast_array.ast_node {
-            let ast_node_type = node.ast_node_type();
-
-            match ast_node_type {
-                // Arithmetic operators (binary)
-                AstNodeType::AdditionNode => {
-                    if expr_stack.len() >= 2 {
-                        let right = expr_stack.pop().unwrap();
-                        let left = expr_stack.pop().unwrap();
-                        expr_stack.push(format!("({}+{})", left, right));
-                    }
-                },
-                AstNodeType::SubtractionNode => {
-                    if expr_stack.len() >= 2 {
-                        let right = expr_stack.pop().unwrap();
-                        let left = expr_stack.pop().unwrap();
-                        expr_stack.push(format!("({}-{})", left, right));
-                    }
-                },
-                AstNodeType::MultiplicationNode => {
-                    if expr_stack.len() >= 2 {
-                        let right = expr_stack.pop().unwrap();
-                        let left = expr_stack.pop().unwrap();
-                        expr_stack.push(format!("({}*{})", left, right));
-                    }
-                },
-                AstNodeType::DivisionNode => {
-                    if expr_stack.len() >= 2 {
-                        let right = expr_stack.pop().unwrap();
-                        let left = expr_stack.pop().unwrap();
-                        expr_stack.push(format!("({}/{})", left, right));
+            match node.ast_node_type() {
+                // Binary operators
+                AstNodeType::AdditionNode => binary(&mut stack, "+", PREC_ADDITIVE),
+                AstNodeType::SubtractionNode => binary(&mut stack, "-", PREC_ADDITIVE),
+                AstNodeType::MultiplicationNode => binary(&mut stack, "*", PREC_MULTIPLICATIVE),
+                AstNodeType::DivisionNode => binary(&mut stack, "/", PREC_MULTIPLICATIVE),
+                AstNodeType::PowerNode => binary(&mut stack, "^", PREC_POWER),
+                AstNodeType::ConcatenationNode => binary(&mut stack, "&", PREC_CONCAT),
+                AstNodeType::GreaterThanNode => binary(&mut stack, ">", PREC_COMPARISON),
+                AstNodeType::GreaterThanOrEqualToNode => binary(&mut stack, ">=", PREC_COMPARISON),
+                AstNodeType::LessThanNode => binary(&mut stack, "<", PREC_COMPARISON),
+                AstNodeType::LessThanOrEqualToNode => binary(&mut stack, "<=", PREC_COMPARISON),
+                AstNodeType::EqualToNode => binary(&mut stack, "=", PREC_COMPARISON),
+                AstNodeType::NotEqualToNode => binary(&mut stack, "<>", PREC_COMPARISON),
+                AstNodeType::ColonNode => binary(&mut stack, ":", PREC_RANGE),
+
+                // Unary operators
+                AstNodeType::NegationNode | AstNodeType::PlusSignNode => {
+                    if let Some((operand, prec)) = stack.pop() {
+                        let sign = if node.ast_node_type() == AstNodeType::NegationNode {
+                            "-"
+                        } else {
+                            "+"
+                        };
+                        stack.push((
+                            format!("{}{}", sign, parenthesize(operand, prec < PREC_UNARY)),
+                            PREC_UNARY,
+                        ));
                     }
                 },
-                AstNodeType::PowerNode => {
-                    if expr_stack.len() >= 2 {
-                        let right = expr_stack.pop().unwrap();
-                        let left = expr_stack.pop().unwrap();
-                        expr_stack.push(format!("({}^{})", left, right));
+                AstNodeType::PercentNode => {
+                    if let Some((operand, prec)) = stack.pop() {
+                        stack.push((
+                            format!("{}%", parenthesize(operand, prec < PREC_PERCENT)),
+                            PREC_PERCENT,
+                        ));
                     }
                 },
 
-                // Note: Comparison operators are handled differently in Numbers AST
-                // They're not separate node types but may be represented through function nodes
-                // For simplicity, we skip explicit handling here and rely on function dispatch
-
                 // Constants
                 AstNodeType::NumberNode => {
                     if let Some(number) = node.ast_number_node_number {
-                        expr_stack.push(number.to_string());
+                        stack.push((number.to_string(), PREC_ATOM));
                     }
                 },
                 AstNodeType::StringNode => {
                     if let Some(ref string) = node.ast_string_node_string {
-                        expr_stack.push(format!("\"{}\"", string));
+                        stack.push((format!("\"{}\"", string.replace('"', "\"\"")), PREC_ATOM));
                     }
                 },
                 AstNodeType::BooleanNode => {
                     if let Some(boolean) = node.ast_boolean_node_boolean {
-                        expr_stack.push(if boolean { "TRUE" } else { "FALSE" }.to_string());
+                        stack.push((
+                            if boolean { "TRUE" } else { "FALSE" }.to_string(),
+                            PREC_ATOM,
+                        ));
+                    }
+                },
+                AstNodeType::DateNode => {
+                    if let Some(date) = node.ast_date_node_date_num.and_then(apple_date) {
+                        stack.push((
+                            format!("\"{}\"", date.format("%Y-%m-%dT%H:%M:%S")),
+                            PREC_ATOM,
+                        ));
                     }
                 },
+                AstNodeType::EmptyArgumentNode => stack.push((String::new(), PREC_ATOM)),
+                AstNodeType::ReferenceErrorNode => stack.push(("#REF!".to_string(), PREC_ATOM)),
 
                 // Cell references
                 AstNodeType::CellReferenceNode => {
+                    if let (Some(column), Some(row_coord)) = (&node.ast_column, &node.ast_row) {
+                        let column_index = if column.absolute() {
+                            i64::from(column.column)
+                        } else {
+                            col as i64 + i64::from(column.column)
+                        };
+                        let row_index = if row_coord.absolute() {
+                            i64::from(row_coord.row)
+                        } else {
+                            row as i64 + i64::from(row_coord.row)
+                        };
+                        let table_prefix = if node.ast_cross_table_reference_extra_info.is_some() {
+                            "Table::"
+                        } else {
+                            ""
+                        };
+                        let text = match (u32::try_from(column_index), u32::try_from(row_index)) {
+                            (Ok(column_index), Ok(row_index)) => format!(
+                                "{}{}{}{}{}",
+                                table_prefix,
+                                if column.absolute() { "$" } else { "" },
+                                Self::column_index_to_letter(column_index),
+                                if row_coord.absolute() { "$" } else { "" },
+                                row_index + 1
+                            ),
+                            _ => "#REF!".to_string(),
+                        };
+                        stack.push((text, PREC_ATOM));
+                    }
+                },
+                AstNodeType::LocalCellReferenceNode => {
                     if let Some(ref cell_ref) = node.ast_local_cell_reference_node_reference {
                         // Convert row/column handles to A1 notation
-                        let col_letter = self.column_index_to_letter(cell_ref.column_handle);
+                        let col_letter = Self::column_index_to_letter(cell_ref.column_handle);
                         let row_num = cell_ref.row_handle + 1; // 0-based to 1-based
                         let col_sticky = if cell_ref.column_is_sticky != 0 {
                             "$"
@@ -464,17 +462,18 @@ impl<'a> TableDataExtractor<'a> {
                             ""
                         };
                         let row_sticky = if cell_ref.row_is_sticky != 0 { "$" } else { "" };
-                        expr_stack.push(format!(
-                            "{}{}{}{}",
-                            col_sticky, col_letter, row_sticky, row_num
+                        stack.push((
+                            format!("{}{}{}{}", col_sticky, col_letter, row_sticky, row_num),
+                            PREC_ATOM,
                         ));
-                    } else if let Some(ref cross_ref) =
-                        node.ast_cross_table_cell_reference_node_reference
+                    }
+                },
+                AstNodeType::CrossTableCellReferenceNode => {
+                    if let Some(ref cross_ref) = node.ast_cross_table_cell_reference_node_reference
                     {
-                        // Cross-table reference
-                        let col_letter = self.column_index_to_letter(cross_ref.column_handle);
+                        let col_letter = Self::column_index_to_letter(cross_ref.column_handle);
                         let row_num = cross_ref.row_handle + 1;
-                        expr_stack.push(format!("{}::{}{}", "Table", col_letter, row_num));
+                        stack.push((format!("Table::{}{}", col_letter, row_num), PREC_ATOM));
                     }
                 },
 
@@ -482,74 +481,55 @@ impl<'a> TableDataExtractor<'a> {
                 AstNodeType::FunctionNode => {
                     if let Some(function_index) = node.ast_function_node_index {
                         let num_args = node.ast_function_node_num_args.unwrap_or(0);
-                        let function_name = self.get_function_name(function_index);
-
-                        // Pop arguments from stack (in reverse order)
-                        let mut args = Vec::new();
-                        for _ in 0..num_args {
-                            if let Some(arg) = expr_stack.pop() {
-                                args.push(arg);
-                            }
-                        }
-                        args.reverse();
-
-                        let args_str = args.join(",");
-                        expr_stack.push(format!("{}({})", function_name, args_str));
+                        let name = Self::get_function_name(function_index);
+                        let args = pop_args(&mut stack, num_args);
+                        stack.push((format!("{}({})", name, args.join(",")), PREC_ATOM));
+                    }
+                },
+                AstNodeType::UnknownFunctionNode => {
+                    if let Some(ref name) = node.ast_unknown_function_node_string {
+                        let num_args = node.ast_unknown_function_node_num_args.unwrap_or(0);
+                        let args = pop_args(&mut stack, num_args);
+                        stack.push((format!("{}({})", name, args.join(",")), PREC_ATOM));
                     }
                 },
 
                 // List (for function arguments)
                 AstNodeType::ListNode => {
                     if let Some(num_args) = node.ast_list_node_num_args {
-                        // Collect arguments
-                        let mut args = Vec::new();
-                        for _ in 0..num_args {
-                            if let Some(arg) = expr_stack.pop() {
-                                args.push(arg);
-                            }
-                        }
-                        args.reverse();
-                        expr_stack.push(args.join(","));
+                        let args = pop_args(&mut stack, num_args);
+                        stack.push((args.join(","), PREC_ATOM));
                     }
                 },
 
-                // Unary operators - represented differently in the AST
-                // Numbers uses NegationNode instead of UnaryMinusNode
-                AstNodeType::NegationNode => {
-                    if let Some(operand) = expr_stack.pop() {
-                        expr_stack.push(format!("-({})", operand));
-                    }
+                // Array constants, written row by row
+                AstNodeType::ArrayNode => {
+                    let columns = node.ast_array_node_num_col.unwrap_or(0).max(1) as usize;
+                    let rows = node.ast_array_node_num_row.unwrap_or(0);
+                    let items = pop_args(&mut stack, columns as u32 * rows);
+                    let rows: Vec<String> =
+                        items.chunks(columns).map(|row| row.join(",")).collect();
+                    stack.push((format!("{{{}}}", rows.join(";")), PREC_ATOM));
                 },
 
-                // Concatenation
-                AstNodeType::ConcatenationNode => {
-                    if expr_stack.len() >= 2 {
-                        let right = expr_stack.pop().unwrap();
-                        let left = expr_stack.pop().unwrap();
-                        expr_stack.push(format!("({}&{})", left, right));
+                // Lazily evaluated arguments (e.g. of IF) carry their own array
+                AstNodeType::ThunkNode => {
+                    if let Some(ref thunk) = node.ast_thunk_node_array {
+                        let text = Self::format_ast(thunk, row, col).unwrap_or_default();
+                        stack.push((text, PREC_ATOM));
                     }
                 },
 
-                // Other node types - handle gracefully
-                _ => {
-                    // Unknown or special node types - keep processing
-                    // (e.g., whitespace nodes, thunk nodes, etc.)
-                },
+                // Whitespace and other structural nodes don't affect the text
+                _ => {},
             }
         }
 
-        // The final result should be on top of the stack
-        let result = if expr_stack.is_empty() {
-            "=FORMULA()".to_string()
-        } else {
-            format!("={}", expr_stack.pop().unwrap())
-        };
-
-        Ok(result)
+        stack.pop().map(|(text, _)| text)
     }
 
     /// Convert column index to Excel-style letter (0 -> A, 1 -> B, ..., 25 -> Z, 26 -> AA)
-    fn column_index_to_letter(&self, index: u32) -> String {
+    fn column_index_to_letter(index: u32) -> String {
         let mut result = String::new();
         let mut idx = index;
 
@@ -567,7 +547,7 @@ impl<'a> TableDataExtractor<'a> {
 
     /// Get function name from function index
     /// Based on Numbers built-in function list
-    fn get_function_name(&self, index: u32) -> String {
+    fn get_function_name(index: u32) -> String {
         // Common function indices (based on analysis of Numbers documents)
         // This mapping comes from observing Numbers files and documentation
         match index {
@@ -635,37 +615,396 @@ impl<'a> TableDataExtractor<'a> {
 
         Ok(None)
     }
+
+    /// Extract rich text from a RichTextPayloadArchive reference
+    fn extract_rich_text_payload(&self, payload_id: u64) -> Result<Option<String>> {
+        if let Some(resolved) = self.object_index.resolve_object(self.bundle, payload_id)? {
+            for msg in &resolved.messages {
+                if msg.type_ == 6218
+                    && let Ok(payload) = tst::RichTextPayloadArchive::decode(&*msg.data)
+                {
+                    return self.extract_rich_text(payload.storage.identifier);
+                }
+            }
+        }
+
+        // Some documents reference the text storage directly
+        self.extract_rich_text(payload_id)
+    }
+}
+
+/// Number of rows stored in each tile
+const ROWS_PER_TILE: usize = 256;
+
+// Cell types in the packed cell storage (TST.CellType)
+const CELL_TYPE_NUMBER: u8 = 2;
+const CELL_TYPE_TEXT: u8 = 3;
+const CELL_TYPE_DATE: u8 = 5;
+const CELL_TYPE_BOOL: u8 = 6;
+const CELL_TYPE_DURATION: u8 = 7;
+const CELL_TYPE_FORMULA_ERROR: u8 = 8;
+const CELL_TYPE_RICH_TEXT: u8 = 9;
+const CELL_TYPE_CURRENCY: u8 = 10;
+
+// Flags announcing the fields present in a stored cell, in storage order
+const FLAG_DECIMAL: u32 = 0x1;
+const FLAG_DOUBLE: u32 = 0x2;
+const FLAG_SECONDS: u32 = 0x4;
+const FLAG_STRING_ID: u32 = 0x8;
+const FLAG_RICH_TEXT_ID: u32 = 0x10;
+const FLAG_FORMULA_ID: u32 = 0x200;
+const FLAG_NUMBER_FORMAT_ID: u32 = 0x2000;
+const FLAG_CURRENCY_FORMAT_ID: u32 = 0x4000;
+
+// TSK.FormatStructArchive format types
+const FORMAT_TYPE_CURRENCY: u32 = 257;
+const FORMAT_TYPE_PERCENTAGE: u32 = 258;
+
+// Operator precedence used when writing formulas, lowest first
+const PREC_COMPARISON: u8 = 1;
+const PREC_CONCAT: u8 = 2;
+const PREC_ADDITIVE: u8 = 3;
+const PREC_MULTIPLICATIVE: u8 = 4;
+const PREC_POWER: u8 = 5;
+const PREC_UNARY: u8 = 6;
+const PREC_PERCENT: u8 = 7;
+const PREC_RANGE: u8 = 8;
+const PREC_ATOM: u8 = 9;
+
+/// TableDataList entries of a table's data store, keyed by the IDs in cells
+struct DataLists {
+    strings: HashMap<u32, ListEntry>,
+    formulas: HashMap<u32, ListEntry>,
+    formats: HashMap<u32, ListEntry>,
+    rich_text: HashMap<u32, ListEntry>,
+}
+
+/// A cell in the packed tile storage
+///
+/// Layout: a version byte (5), the cell type, six reserved bytes and a
+/// 32-bit flags word, followed by the fields the flags announce in flag
+/// order: a 128-bit decimal, a double, a date in seconds and 32-bit keys
+/// into the data store lists.
+struct StoredCell<'a> {
+    cell_type: u8,
+    flags: u32,
+    fields: &'a [u8],
+}
+
+impl<'a> StoredCell<'a> {
+    /// Parse the cell header at the start of `data`
+    fn parse(data: &'a [u8]) -> Option<Self> {
+        if data.len() < 12 || data[0] != 5 {
+            return None;
+        }
+        Some(Self {
+            cell_type: data[1],
+            flags: u32::from_le_bytes(data[8..12].try_into().ok()?),
+            fields: &data[12..],
+        })
+    }
+
+    /// Read the field announced by `flag`, if present
+    fn field<const N: usize>(&self, flag: u32) -> Option<[u8; N]> {
+        if self.flags & flag == 0 {
+            return None;
+        }
+        // Skip the fields of all lower flags; everything after the date is 4 bytes
+        let before = self.flags & (flag - 1);
+        let mut offset = 4 * (before & !(FLAG_DECIMAL | FLAG_DOUBLE | FLAG_SECONDS)).count_ones();
+        if before & FLAG_DECIMAL != 0 {
+            offset += 16;
+        }
+        if before & FLAG_DOUBLE != 0 {
+            offset += 8;
+        }
+        if before & FLAG_SECONDS != 0 {
+            offset += 8;
+        }
+        let offset = offset as usize;
+        self.fields.get(offset..offset + N)?.try_into().ok()
+    }
+
+    /// Numeric value, preferring the exact decimal
+    fn number(&self) -> Option<f64> {
+        self.field(FLAG_DECIMAL)
+            .map(decode_decimal128)
+            .or_else(|| self.field(FLAG_DOUBLE).map(f64::from_le_bytes))
+    }
+
+    /// Date as seconds since 2001-01-01
+    fn seconds(&self) -> Option<f64> {
+        self.field(FLAG_SECONDS).map(f64::from_le_bytes)
+    }
+
+    /// Key into one of the data store lists
+    fn id(&self, flag: u32) -> Option<u32> {
+        self.field(flag).map(u32::from_le_bytes)
+    }
+}
+
+/// Decode an IEEE 754 decimal128 value with a binary integer significand
+fn decode_decimal128(bytes: [u8; 16]) -> f64 {
+    let exponent = ((i32::from(bytes[15] & 0x7F) << 7) | i32::from(bytes[14] >> 1)) - 0x1820;
+    let mut significand = u128::from(bytes[14] & 1);
+    for &byte in bytes[..14].iter().rev() {
+        significand = (significand << 8) | u128::from(byte);
+    }
+
+    // Dividing by an exact power of ten keeps values like 0.1 exact
+    let magnitude = if exponent < 0 {
+        significand as f64 / 10f64.powi(-exponent)
+    } else {
+        significand as f64 * 10f64.powi(exponent)
+    };
+    if bytes[15] & 0x80 != 0 {
+        -magnitude
+    } else {
+        magnitude
+    }
+}
+
+/// Convert seconds since Apple's 2001-01-01 epoch to a date and time
+fn apple_date(seconds: f64) -> Option<NaiveDateTime> {
+    let epoch = NaiveDate::from_ymd_opt(2001, 1, 1)?.and_hms_opt(0, 0, 0)?;
+    let millis = (seconds * 1000.0).round();
+    if !millis.is_finite() {
+        return None;
+    }
+    epoch.checked_add_signed(TimeDelta::try_milliseconds(millis as i64)?)
+}
+
+/// The currency or percentage hint of a number format
+fn number_format(format: &tsk::FormatStructArchive) -> Option<NumberFormat> {
+    match format.format_type {
+        FORMAT_TYPE_CURRENCY => Some(NumberFormat::Currency(
+            format.currency_code.clone().unwrap_or_default(),
+        )),
+        FORMAT_TYPE_PERCENTAGE => Some(NumberFormat::Percentage),
+        _ => None,
+    }
+}
+
+/// Wrap `text` in parentheses when `needed`
+fn parenthesize(text: String, needed: bool) -> String {
+    if needed { format!("({})", text) } else { text }
+}
+
+/// Replace the top two expressions with a left-associative binary operation
+fn binary(stack: &mut Vec<(String, u8)>, operator: &str, prec: u8) {
+    if stack.len() < 2 {
+        return;
+    }
+    let (right, right_prec) = stack.pop().unwrap();
+    let (left, left_prec) = stack.pop().unwrap();
+    stack.push((
+        format!(
+            "{}{}{}",
+            parenthesize(left, left_prec < prec),
+            operator,
+            parenthesize(right, right_prec <= prec)
+        ),
+        prec,
+    ));
+}
+
+/// Pop up to `count` expressions, returning them in source order
+fn pop_args(stack: &mut Vec<(String, u8)>, count: u32) -> Vec<String> {
+    let start = stack.len().saturating_sub(count as usize);
+    stack.drain(start..).map(|(text, _)| text).collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::iwa::protobuf::tsce::ast_node_array_archive::{
+        AstColumnCoordinateArchive, AstNodeArchive, AstNodeType, AstRowCoordinateArchive,
+    };
+
+    /// Encode a decimal128 value the way Numbers stores it
+    fn decimal(significand: u64, exponent: i32, negative: bool) -> [u8; 16] {
+        let mut bytes = [0u8; 16];
+        bytes[..8].copy_from_slice(&significand.to_le_bytes());
+        let biased = (exponent + 0x1820) as u32;
+        bytes[14] = ((biased & 0x7F) << 1) as u8;
+        bytes[15] = ((biased >> 7) & 0x7F) as u8 | if negative { 0x80 } else { 0 };
+        bytes
+    }
+
+    fn stored_cell(cell_type: u8, flags: u32, fields: &[u8]) -> Vec<u8> {
+        let mut data = vec![5, cell_type, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(&flags.to_le_bytes());
+        data.extend_from_slice(fields);
+        data
+    }
+
+    fn node(node_type: AstNodeType) -> AstNodeArchive {
+        AstNodeArchive {
+            ast_node_type: node_type as i32,
+            ..Default::default()
+        }
+    }
+
+    fn number(value: f64) -> AstNodeArchive {
+        AstNodeArchive {
+            ast_number_node_number: Some(value),
+            ..node(AstNodeType::NumberNode)
+        }
+    }
+
+    fn reference(column: i32, row: i32, absolute: bool) -> AstNodeArchive {
+        AstNodeArchive {
+            ast_column: Some(AstColumnCoordinateArchive {
+                column,
+                absolute: Some(absolute),
+            }),
+            ast_row: Some(AstRowCoordinateArchive {
+                row,
+                absolute: Some(absolute),
+            }),
+            ..node(AstNodeType::CellReferenceNode)
+        }
+    }
+
+    fn formula(nodes: Vec<AstNodeArchive>) -> tsce::FormulaArchive {
+        tsce::FormulaArchive {
+            ast_node_array: tsce::AstNodeArrayArchive { ast_node: nodes },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_cell_offsets() {
+        let data = [0x00, 0x00, 0xFF, 0xFF, 0x20, 0x00];
+        assert_eq!(
+            TableDataExtractor::parse_cell_offsets(&data, false),
+            vec![Some(0), None, Some(32)]
+        );
+        assert_eq!(
+            TableDataExtractor::parse_cell_offsets(&data, true),
+            vec![Some(0), None, Some(128)]
+        );
+    }
+
+    #[test]
+    fn test_decimal128() {
+        assert_eq!(decode_decimal128(decimal(42, 0, false)), 42.0);
+        assert_eq!(decode_decimal128(decimal(1, -1, false)), 0.1);
+        assert_eq!(decode_decimal128(decimal(12345, -2, true)), -123.45);
+        assert_eq!(decode_decimal128(decimal(7, 3, false)), 7000.0);
+    }
+
+    #[test]
+    fn test_stored_cell_fields() {
+        let mut fields = Vec::new();
+        fields.extend_from_slice(&decimal(25, -2, false));
+        fields.extend_from_slice(&0.25f64.to_le_bytes());
+        fields.extend_from_slice(&7u32.to_le_bytes()); // formula
+        fields.extend_from_slice(&3u32.to_le_bytes()); // number format
+        let data = stored_cell(
+            CELL_TYPE_NUMBER,
+            FLAG_DECIMAL | FLAG_DOUBLE | FLAG_FORMULA_ID | FLAG_NUMBER_FORMAT_ID,
+            &fields,
+        );
+
+        let cell = StoredCell::parse(&data).unwrap();
+        assert_eq!(cell.cell_type, CELL_TYPE_NUMBER);
+        assert_eq!(cell.number(), Some(0.25));
+        assert_eq!(cell.id(FLAG_FORMULA_ID), Some(7));
+        assert_eq!(cell.id(FLAG_NUMBER_FORMAT_ID), Some(3));
+        assert_eq!(cell.id(FLAG_STRING_ID), None);
+        assert_eq!(cell.seconds(), None);
+
+        // Unsupported storage versions and truncated data are ignored
+        assert!(StoredCell::parse(&[4, 2, 0, 0]).is_none());
+        let truncated = stored_cell(CELL_TYPE_TEXT, FLAG_STRING_ID, &[1, 0]);
+        assert_eq!(
+            StoredCell::parse(&truncated).unwrap().id(FLAG_STRING_ID),
+            None
+        );
+    }
 
     #[test]
-    fn test_varint_decoding() {
-        // Test single-byte varint: 0
-        let data = vec![0x00];
-        let (value, bytes) = TableDataExtractor::decode_varint(&data).unwrap();
-        assert_eq!(value, 0);
-        assert_eq!(bytes, 1);
-
-        // Test single-byte varint: 127
-        let data = vec![0x7F];
-        let (value, bytes) = TableDataExtractor::decode_varint(&data).unwrap();
-        assert_eq!(value, 127);
-        assert_eq!(bytes, 1);
-
-        // Test two-byte varint: 300 = 0b100101100
-        // Encoded as: 0xAC 0x02 (10101100 00000010)
-        let data = vec![0xAC, 0x02];
-        let (value, bytes) = TableDataExtractor::decode_varint(&data).unwrap();
-        assert_eq!(value, 300);
-        assert_eq!(bytes, 2);
+    fn test_apple_date() {
+        let date = apple_date(0.0).unwrap();
+        assert_eq!(date.to_string(), "2001-01-01 00:00:00");
+        // 2024-02-29 13:30:00
+        let date = apple_date(730_906_200.0).unwrap();
+        assert_eq!(
+            date.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            "2024-02-29T13:30:00"
+        );
+        assert!(apple_date(f64::NAN).is_none());
     }
 
     #[test]
-    fn test_cell_value_parsing() {
-        // Test would require actual protobuf messages
-        // Placeholder test
+    fn test_number_format() {
+        let mut format = tsk::FormatStructArchive {
+            format_type: FORMAT_TYPE_CURRENCY,
+            currency_code: Some("JPY".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            number_format(&format),
+            Some(NumberFormat::Currency("JPY".to_string()))
+        );
+        format.format_type = FORMAT_TYPE_PERCENTAGE;
+        assert_eq!(number_format(&format), Some(NumberFormat::Percentage));
+        format.format_type = 256;
+        assert_eq!(number_format(&format), None);
+    }
+
+    #[test]
+    fn test_formula_string() {
+        // (A1+B1)*2 written in the cell at C1
+        let product = formula(vec![
+            reference(-2, 0, false),
+            reference(-1, 0, false),
+            node(AstNodeType::AdditionNode),
+            number(2.0),
+            node(AstNodeType::MultiplicationNode),
+        ]);
+        assert_eq!(
+            TableDataExtractor::extract_formula_string(&product, 0, 2),
+            "=(A1+B1)*2"
+        );
+
+        // SUM($A$1:A3)>10 written in the cell at B4
+        let sum = formula(vec![
+            reference(0, 0, true),
+            reference(-1, -1, false),
+            node(AstNodeType::ColonNode),
+            AstNodeArchive {
+                ast_function_node_index: Some(0),
+                ast_function_node_num_args: Some(1),
+                ..node(AstNodeType::FunctionNode)
+            },
+            number(10.0),
+            node(AstNodeType::GreaterThanNode),
+        ]);
+        assert_eq!(
+            TableDataExtractor::extract_formula_string(&sum, 3, 1),
+            "=SUM($A$1:A3)>10"
+        );
+
+        // 1-(2-3), and references before the first column
+        let nested = formula(vec![
+            number(1.0),
+            number(2.0),
+            number(3.0),
+            node(AstNodeType::SubtractionNode),
+            node(AstNodeType::SubtractionNode),
+            reference(-5, 0, false),
+            node(AstNodeType::AdditionNode),
+        ]);
+        assert_eq!(
+            TableDataExtractor::extract_formula_string(&nested, 0, 0),
+            "=1-(2-3)+#REF!"
+        );
+
+        assert_eq!(
+            TableDataExtractor::extract_formula_string(&formula(Vec::new()), 0, 0),
+            "="
+        );
     }
 }
@@ -8,8 +8,17 @@ use super::section::{PagesSection, PagesSectionType};
 use crate::iwa::Result;
 use crate::iwa::bundle::Bundle;
 use crate::iwa::object_index::ObjectIndex;
+use crate::iwa::protobuf::{tp, tswp};
 use crate::iwa::registry::Application;
-use crate::iwa::text::TextExtractor;
+use crate::iwa::structured::{self, Table};
+use crate::iwa::text::{StyledParagraph, StyledTextExtractor, TextExtractor};
+use prost::Message;
+
+/// Message type of TP.DocumentArchive
+const DOCUMENT_TYPE: u32 = 10000;
+
+/// Message type of TSWP.StorageArchive
+const STORAGE_TYPE: u32 = 2001;
 
 /// High-level interface for Pages documents
 pub struct PagesDocument {
@@ -112,11 +121,98 @@ impl PagesDocument {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn text(&self) -> Result<String> {
+        let paragraphs = self.paragraphs()?;
+        if !paragraphs.is_empty() {
+            return Ok(paragraphs
+                .iter()
+                .map(StyledParagraph::text)
+                .collect::<Vec<_>>()
+                .join("\n"));
+        }
+
+        // Documents without a body storage fall back to all text storages
         let mut extractor = TextExtractor::new();
         extractor.extract_from_bundle(&self.bundle)?;
         Ok(extractor.get_text())
     }
 
+    /// Extract the paragraphs of the document body with their character formatting
+    ///
+    /// The body is the storage referenced by TP.DocumentArchive; documents
+    /// without one use every storage of kind BODY in the bundle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::iwa::pages::PagesDocument;
+    ///
+    /// let doc = PagesDocument::open("document.pages")?;
+    /// for para in doc.paragraphs()? {
+    ///     for run in &para.runs {
+    ///         println!("{} (bold: {})", run.text, run.style.bold);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn paragraphs(&self) -> Result<Vec<StyledParagraph>> {
+        let extractor = StyledTextExtractor::new(&self.bundle, &self.object_index);
+        let mut paragraphs = Vec::new();
+
+        for storage in self.body_storages()? {
+            paragraphs.extend(extractor.paragraphs(&storage)?);
+        }
+
+        Ok(paragraphs)
+    }
+
+    /// Extract the tables of the document
+    pub fn tables(&self) -> Result<Vec<Table>> {
+        structured::extract_tables(&self.bundle, &self.object_index)
+    }
+
+    /// Find the storages that make up the document body
+    fn body_storages(&self) -> Result<Vec<tswp::StorageArchive>> {
+        for (_archive_name, object) in self.bundle.find_objects_by_type(DOCUMENT_TYPE) {
+            if let Some(msg) = object
+                .messages
+                .iter()
+                .find(|msg| msg.type_ == DOCUMENT_TYPE)
+                && let Ok(document) = tp::DocumentArchive::decode(&*msg.data)
+                && let Some(body) = document.body_storage
+                && let Some(storage) = self.storage(body.identifier)?
+            {
+                return Ok(vec![storage]);
+            }
+        }
+
+        let body_kind = tswp::storage_archive::KindType::Body as i32;
+        Ok(self
+            .bundle
+            .find_objects_by_type(STORAGE_TYPE)
+            .into_iter()
+            .filter_map(|(_archive_name, object)| {
+                object.messages.iter().find(|msg| msg.type_ == STORAGE_TYPE)
+            })
+            .filter_map(|msg| tswp::StorageArchive::decode(&*msg.data).ok())
+            .filter(|storage| storage.kind == Some(body_kind))
+            .collect())
+    }
+
+    /// Resolve and decode a TSWP.StorageArchive
+    fn storage(&self, storage_id: u64) -> Result<Option<tswp::StorageArchive>> {
+        if let Some(resolved) = self.object_index.resolve_object(&self.bundle, storage_id)? {
+            for msg in &resolved.messages {
+                if msg.type_ == STORAGE_TYPE
+                    && let Ok(storage) = tswp::StorageArchive::decode(&*msg.data)
+                {
+                    return Ok(Some(storage));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Extract sections from the document
     ///
     /// Pages documents are organized into sections. This method parses the
@@ -7,9 +7,13 @@
 
 use std::collections::HashMap;
 
+use chrono::NaiveDateTime;
+
 use crate::iwa::Result;
 use crate::iwa::bundle::Bundle;
 use crate::iwa::charts::metadata_extractor::ChartMetadataExtractor;
+use crate::iwa::keynote::{SlideShape, SlideShapeExtractor};
+pub use crate::iwa::numbers::NumberFormat;
 use crate::iwa::numbers::table_extractor::TableDataExtractor;
 use crate::iwa::object_index::ObjectIndex;
 use crate::iwa::shapes::text_extractor::ShapeTextExtractor;
@@ -25,6 +29,8 @@ pub struct Table {
     pub column_count: usize,
     /// Cell data (row, column) -> value
     pub cells: HashMap<(usize, usize), CellValue>,
+    /// Formula text (row, column) -> formula, for cells computed by a formula
+    pub formulas: HashMap<(usize, usize), String>,
 }
 
 impl Table {
@@ -35,6 +41,7 @@ impl Table {
             row_count: 0,
             column_count: 0,
             cells: HashMap::new(),
+            formulas: HashMap::new(),
         }
     }
 
@@ -43,6 +50,18 @@ impl Table {
         self.cells.get(&(row, col))
     }
 
+    /// Get the value of a cell, or `CellValue::Empty` if it has none
+    ///
+    /// For formula cells this is the result Numbers last computed.
+    pub fn cell(&self, row: usize, col: usize) -> CellValue {
+        self.get_cell(row, col).cloned().unwrap_or(CellValue::Empty)
+    }
+
+    /// Get the formula of a cell, such as `"=SUM(A1:A3)"`
+    pub fn formula(&self, row: usize, col: usize) -> Option<String> {
+        self.formulas.get(&(row, col)).cloned()
+    }
+
     /// Set a cell value at the specified position
     pub fn set_cell(&mut self, row: usize, col: usize, value: CellValue) {
         self.cells.insert((row, col), value);
@@ -69,16 +88,27 @@ impl Table {
 }
 
 /// Represents a cell value in a table
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum CellValue {
     /// Text/string value
     Text(String),
     /// Numeric value
     Number(f64),
+    /// Numeric value displayed with a currency or percentage format
+    FormattedNumber {
+        /// Raw value, e.g. `0.25` for a cell showing "25%"
+        value: f64,
+        /// How Numbers displays the value
+        format: NumberFormat,
+    },
     /// Boolean value
     Boolean(bool),
-    /// Date value (as string)
-    Date(String),
+    /// Date and time value
+    Date(NaiveDateTime),
+    /// Duration in seconds
+    Duration(f64),
+    /// Error value
+    Error(String),
     /// Formula (stored as string)
     Formula(String),
     /// Empty cell
@@ -96,9 +126,13 @@ impl std::fmt::Display for CellValue {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             CellValue::Text(s) => write!(f, "{}", s),
-            CellValue::Number(n) => write!(f, "{}", n),
+            CellValue::Number(n) | CellValue::FormattedNumber { value: n, .. } => {
+                write!(f, "{}", n)
+            },
             CellValue::Boolean(b) => write!(f, "{}", b),
-            CellValue::Date(d) => write!(f, "{}", d),
+            CellValue::Date(d) => write!(f, "{}", d.format("%Y-%m-%dT%H:%M:%S")),
+            CellValue::Duration(d) => write!(f, "{}", d),
+            CellValue::Error(e) => write!(f, "{}", e),
             CellValue::Formula(formula) => write!(f, "{}", formula),
             CellValue::Empty => Ok(()),
         }
@@ -116,6 +150,10 @@ pub struct Slide {
     pub text_content: Vec<String>,
     /// Notes associated with the slide
     pub notes: Option<String>,
+    /// Name of the master slide (layout) the slide is based on
+    pub layout_name: Option<String>,
+    /// Placeholders and drawables on the slide
+    pub shapes: Vec<SlideShape>,
 }
 
 impl Slide {
@@ -126,9 +164,26 @@ impl Slide {
             title: None,
             text_content: Vec::new(),
             notes: None,
+            layout_name: None,
+            shapes: Vec::new(),
         }
     }
 
+    /// Get the shapes on the slide with their geometry, text and media
+    pub fn shapes(&self) -> &[SlideShape] {
+        &self.shapes
+    }
+
+    /// Get the presenter notes
+    pub fn presenter_notes(&self) -> Option<&str> {
+        self.notes.as_deref()
+    }
+
+    /// Get the name of the slide's layout, e.g. "Title & Bullets"
+    pub fn layout_name(&self) -> Option<&str> {
+        self.layout_name.as_deref()
+    }
+
     /// Get all text from the slide (title + content + notes)
     pub fn all_text(&self) -> Vec<String> {
         let mut all = Vec::new();
@@ -190,6 +245,7 @@ pub fn extract_tables(bundle: &Bundle, object_index: &ObjectIndex) -> Result<Vec
             let mut table = Table::new(nt.name.clone());
             table.row_count = nt.row_count;
             table.column_count = nt.column_count;
+            table.formulas = nt.formulas;
 
             // Convert cells from NumbersTable format to our CellValue format
             for ((row, col), cell) in nt.cells {
@@ -212,17 +268,24 @@ fn convert_numbers_cell_to_structured(cell: crate::iwa::numbers::CellValue) -> C
         NC::Empty => CellValue::Empty,
         NC::Text(s) => CellValue::Text(s),
         NC::Number(n) => CellValue::Number(n),
+        NC::FormattedNumber { value, format } => CellValue::FormattedNumber { value, format },
         NC::Boolean(b) => CellValue::Boolean(b),
         NC::Date(d) => CellValue::Date(d),
-        NC::Duration(_) => CellValue::Empty, // Duration not supported in structured format
+        NC::Duration(d) => CellValue::Duration(d),
         NC::Formula(f) => CellValue::Formula(f),
-        NC::Error(e) => CellValue::Text(format!("ERROR: {}", e)),
+        NC::Error(e) => CellValue::Error(e),
     }
 }
 
 /// Extract slides from a Keynote presentation
-pub fn extract_slides(bundle: &Bundle, _object_index: &ObjectIndex) -> Result<Vec<Slide>> {
+///
+/// Shapes, presenter notes and layout names are resolved through the
+/// object index from each slide's KN.SlideArchive.
+pub fn extract_slides(bundle: &Bundle, object_index: &ObjectIndex) -> Result<Vec<Slide>> {
+    use prost::Message;
+
     let mut slides = Vec::new();
+    let shape_extractor = SlideShapeExtractor::new(bundle, object_index);
 
     // Find all slide objects (message type 1102 based on our decoder map)
     let slide_objects = bundle.find_objects_by_type(1102);
@@ -237,6 +300,15 @@ pub fn extract_slides(bundle: &Bundle, _object_index: &ObjectIndex) -> Result<Ve
             slide.text_content = text_parts.into_iter().skip(1).collect();
         }
 
+        if let Some(raw_message) = object.messages.first()
+            && let Ok(slide_archive) =
+                crate::iwa::protobuf::kn::SlideArchive::decode(&*raw_message.data)
+        {
+            slide.shapes = shape_extractor.shapes(&slide_archive)?;
+            slide.notes = shape_extractor.presenter_notes(&slide_archive)?;
+            slide.layout_name = shape_extractor.layout_name(&slide_archive)?;
+        }
+
         slides.push(slide);
     }
 
@@ -475,6 +547,39 @@ mod tests {
 
         let number_cell = CellValue::Number(std::f64::consts::PI);
         assert_eq!(number_cell.to_string(), "3.141592653589793");
+
+        let percent_cell = CellValue::FormattedNumber {
+            value: 0.25,
+            format: NumberFormat::Percentage,
+        };
+        assert_eq!(percent_cell.to_string(), "0.25");
+
+        let date = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+            .and_then(|d| d.and_hms_opt(13, 30, 0))
+            .unwrap();
+        assert_eq!(CellValue::Date(date).to_string(), "2024-02-29T13:30:00");
+    }
+
+    #[test]
+    fn test_table_cell_and_formula() {
+        let mut table = Table::new("Totals".to_string());
+        table.set_cell(0, 0, CellValue::Number(2.0));
+        table.set_cell(1, 0, CellValue::Number(3.0));
+        table.set_cell(
+            2,
+            0,
+            CellValue::FormattedNumber {
+                value: 5.0,
+                format: NumberFormat::Currency("USD".to_string()),
+            },
+        );
+        table.formulas.insert((2, 0), "=SUM(A1:A2)".to_string());
+
+        assert_eq!(table.cell(0, 0), CellValue::Number(2.0));
+        assert_eq!(table.cell(5, 5), CellValue::Empty);
+        assert_eq!(table.formula(2, 0).as_deref(), Some("=SUM(A1:A2)"));
+        assert_eq!(table.formula(0, 0), None);
+        assert_eq!(table.to_csv(), "2\n3\n5\n");
     }
 
     #[test]
@@ -494,6 +599,31 @@ mod tests {
         assert_eq!(all_text[3], "Speaker notes");
     }
 
+    #[test]
+    fn test_slide_accessors() {
+        use crate::iwa::keynote::ShapeKind;
+
+        let mut slide = Slide::new(0);
+        assert!(slide.shapes().is_empty());
+        assert_eq!(slide.presenter_notes(), None);
+        assert_eq!(slide.layout_name(), None);
+
+        slide.notes = Some("Remember the demo".to_string());
+        slide.layout_name = Some("Title & Photo".to_string());
+        let mut image = SlideShape::new(42, ShapeKind::Image);
+        image.media_filename = Some("photo-42.jpeg".to_string());
+        image.build_order = Some(0);
+        slide.shapes.push(image);
+
+        assert_eq!(slide.presenter_notes(), Some("Remember the demo"));
+        assert_eq!(slide.layout_name(), Some("Title & Photo"));
+        assert_eq!(slide.shapes()[0].kind, ShapeKind::Image);
+        assert_eq!(
+            slide.shapes()[0].media_filename.as_deref(),
+            Some("photo-42.jpeg")
+        );
+    }
+
     #[test]
     fn test_section_creation() {
         let mut section = Section::new(0);
@@ -0,0 +1,314 @@
+//! Styled Paragraph Extraction
+//!
+//! A TSWP.StorageArchive keeps its text as a single string with attribute
+//! tables keyed by character index (in UTF-16 code units). Paragraph styles
+//! start at the entries of `table_para_style` and character styles at the
+//! entries of `table_char_style`; both reference style archives that are
+//! resolved through the object index.
+
+use std::collections::HashMap;
+
+use super::style::TextStyle;
+use crate::iwa::Result;
+use crate::iwa::bundle::Bundle;
+use crate::iwa::object_index::ObjectIndex;
+use crate::iwa::protobuf::tswp;
+use prost::Message;
+
+/// Message type of TSWP.CharacterStyleArchive
+const CHARACTER_STYLE_TYPE: u32 = 2021;
+
+/// Message type of TSWP.ParagraphStyleArchive
+const PARAGRAPH_STYLE_TYPE: u32 = 2022;
+
+/// Maximum length of a style's parent chain that is followed
+const MAX_STYLE_DEPTH: usize = 16;
+
+/// Character properties of a style and its parents, root style first
+type StyleChain = Vec<tswp::CharacterStylePropertiesArchive>;
+
+/// A run of text with uniform character formatting
+#[derive(Debug, Clone, Default)]
+pub struct StyledRun {
+    /// The text content
+    pub text: String,
+    /// Effective character formatting
+    pub style: TextStyle,
+}
+
+/// A paragraph of a text storage, split into formatted runs
+#[derive(Debug, Clone, Default)]
+pub struct StyledParagraph {
+    /// Runs in reading order
+    pub runs: Vec<StyledRun>,
+}
+
+impl StyledParagraph {
+    /// Get the plain text of the paragraph
+    pub fn text(&self) -> String {
+        self.runs.iter().map(|run| run.text.as_str()).collect()
+    }
+
+    /// Check if the paragraph has no text
+    pub fn is_empty(&self) -> bool {
+        self.runs.iter().all(|run| run.text.is_empty())
+    }
+}
+
+/// Extractor for paragraphs with resolved character formatting
+pub struct StyledTextExtractor<'a> {
+    bundle: &'a Bundle,
+    object_index: &'a ObjectIndex,
+}
+
+impl<'a> StyledTextExtractor<'a> {
+    /// Create a new styled text extractor
+    pub fn new(bundle: &'a Bundle, object_index: &'a ObjectIndex) -> Self {
+        Self {
+            bundle,
+            object_index,
+        }
+    }
+
+    /// Split a storage into paragraphs and resolve the formatting of its runs
+    pub fn paragraphs(&self, storage: &tswp::StorageArchive) -> Result<Vec<StyledParagraph>> {
+        let mut chains = HashMap::new();
+
+        let tables = [&storage.table_para_style, &storage.table_char_style];
+        for entry in tables
+            .into_iter()
+            .flatten()
+            .flat_map(|table| &table.entries)
+        {
+            if let Some(reference) = &entry.object
+                && !chains.contains_key(&reference.identifier)
+            {
+                let chain = self.style_chain(reference.identifier)?;
+                chains.insert(reference.identifier, chain);
+            }
+        }
+
+        Ok(split_paragraphs(storage, &chains))
+    }
+
+    /// Collect the character properties of a style and its parents
+    fn style_chain(&self, style_id: u64) -> Result<StyleChain> {
+        let mut chain = Vec::new();
+        let mut next = Some(style_id);
+
+        for _ in 0..MAX_STYLE_DEPTH {
+            let Some(id) = next.take() else {
+                break;
+            };
+            let Some(resolved) = self.object_index.resolve_object(self.bundle, id)? else {
+                break;
+            };
+
+            for msg in &resolved.messages {
+                let decoded = match msg.type_ {
+                    CHARACTER_STYLE_TYPE => tswp::CharacterStyleArchive::decode(&*msg.data)
+                        .ok()
+                        .map(|style| (style.super_, style.char_properties)),
+                    PARAGRAPH_STYLE_TYPE => tswp::ParagraphStyleArchive::decode(&*msg.data)
+                        .ok()
+                        .map(|style| (style.super_, style.char_properties)),
+                    _ => None,
+                };
+
+                if let Some((style, properties)) = decoded {
+                    next = style.parent.map(|parent| parent.identifier);
+                    chain.extend(properties);
+                    break;
+                }
+            }
+        }
+
+        chain.reverse();
+        Ok(chain)
+    }
+}
+
+/// Map the character indices of an attribute table to the referenced styles
+fn style_starts(table: Option<&tswp::ObjectAttributeTable>) -> HashMap<usize, Option<u64>> {
+    table
+        .map(|table| {
+            table
+                .entries
+                .iter()
+                .map(|entry| {
+                    let style = entry.object.as_ref().map(|reference| reference.identifier);
+                    (entry.character_index as usize, style)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Split the text of a storage at paragraph breaks, starting a new run
+/// wherever the effective formatting changes
+///
+/// Attachment markers and control characters other than tabs are dropped.
+fn split_paragraphs(
+    storage: &tswp::StorageArchive,
+    chains: &HashMap<u64, StyleChain>,
+) -> Vec<StyledParagraph> {
+    let para_starts = style_starts(storage.table_para_style.as_ref());
+    let char_starts = style_starts(storage.table_char_style.as_ref());
+    let mut effective: HashMap<(Option<u64>, Option<u64>), TextStyle> = HashMap::new();
+
+    let mut paragraphs = Vec::new();
+    let mut current = StyledParagraph::default();
+    let mut current_key = None;
+    let (mut para_style, mut char_style) = (None, None);
+    let mut index = 0;
+
+    for ch in storage.text.concat().chars() {
+        if let Some(&style) = para_starts.get(&index) {
+            para_style = style;
+        }
+        if let Some(&style) = char_starts.get(&index) {
+            char_style = style;
+        }
+        index += ch.len_utf16();
+
+        match ch {
+            '\n' | '\u{2029}' => {
+                paragraphs.push(std::mem::take(&mut current));
+                current_key = None;
+                continue;
+            },
+            '\u{fffc}' => continue,
+            c if c.is_control() && c != '\t' => continue,
+            _ => {},
+        }
+
+        let key = (para_style, char_style);
+        match current.runs.last_mut() {
+            Some(run) if current_key == Some(key) => run.text.push(ch),
+            _ => {
+                let style = effective
+                    .entry(key)
+                    .or_insert_with(|| {
+                        let mut style = TextStyle::new();
+                        for id in [para_style, char_style].into_iter().flatten() {
+                            for properties in chains.get(&id).into_iter().flatten() {
+                                style.apply(properties);
+                            }
+                        }
+                        style
+                    })
+                    .clone();
+                current.runs.push(StyledRun {
+                    text: ch.to_string(),
+                    style,
+                });
+                current_key = Some(key);
+            },
+        }
+    }
+
+    if !current.runs.is_empty() {
+        paragraphs.push(current);
+    }
+
+    paragraphs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::iwa::protobuf::tsp;
+
+    fn attribute(
+        character_index: u32,
+        style: u64,
+    ) -> tswp::object_attribute_table::ObjectAttribute {
+        tswp::object_attribute_table::ObjectAttribute {
+            character_index,
+            object: Some(tsp::Reference {
+                identifier: style,
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn test_split_paragraphs_with_runs() {
+        let storage = tswp::StorageArchive {
+            text: vec!["Plain bold\nItalic \u{fffc}tail\n".to_string()],
+            table_para_style: Some(tswp::ObjectAttributeTable {
+                entries: vec![attribute(0, 1), attribute(11, 2)],
+            }),
+            table_char_style: Some(tswp::ObjectAttributeTable {
+                entries: vec![
+                    attribute(6, 3),
+                    tswp::object_attribute_table::ObjectAttribute {
+                        character_index: 10,
+                        object: None,
+                    },
+                ],
+            }),
+            ..Default::default()
+        };
+
+        let mut chains = HashMap::new();
+        chains.insert(1, vec![tswp::CharacterStylePropertiesArchive::default()]);
+        chains.insert(
+            2,
+            vec![tswp::CharacterStylePropertiesArchive {
+                italic: Some(true),
+                ..Default::default()
+            }],
+        );
+        chains.insert(
+            3,
+            vec![tswp::CharacterStylePropertiesArchive {
+                bold: Some(true),
+                ..Default::default()
+            }],
+        );
+
+        let paragraphs = split_paragraphs(&storage, &chains);
+        assert_eq!(paragraphs.len(), 2);
+
+        let first = &paragraphs[0];
+        assert_eq!(first.text(), "Plain bold");
+        assert_eq!(first.runs.len(), 2);
+        assert_eq!(first.runs[0].text, "Plain ");
+        assert!(!first.runs[0].style.bold);
+        assert_eq!(first.runs[1].text, "bold");
+        assert!(first.runs[1].style.bold);
+
+        let second = &paragraphs[1];
+        assert_eq!(second.text(), "Italic tail");
+        assert_eq!(second.runs.len(), 1);
+        assert!(second.runs[0].style.italic);
+        assert!(!second.runs[0].style.bold);
+    }
+
+    #[test]
+    fn test_split_paragraphs_utf16_indices() {
+        // The emoji takes two UTF-16 code units, so "b" starts at index 3
+        let storage = tswp::StorageArchive {
+            text: vec!["a\u{1F600}b".to_string()],
+            table_char_style: Some(tswp::ObjectAttributeTable {
+                entries: vec![attribute(3, 1)],
+            }),
+            ..Default::default()
+        };
+        let mut chains = HashMap::new();
+        chains.insert(
+            1,
+            vec![tswp::CharacterStylePropertiesArchive {
+                bold: Some(true),
+                ..Default::default()
+            }],
+        );
+
+        let paragraphs = split_paragraphs(&storage, &chains);
+        assert_eq!(paragraphs.len(), 1);
+        assert_eq!(paragraphs[0].runs.len(), 2);
+        assert_eq!(paragraphs[0].runs[1].text, "b");
+        assert!(paragraphs[0].runs[1].style.bold);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! iWork documents support rich text with character-level and paragraph-level styling.
 
+use crate::iwa::protobuf::tswp;
+
 /// Text style properties (character-level)
 #[derive(Debug, Clone, Default)]
 pub struct TextStyle {
@@ -17,6 +19,10 @@ pub struct TextStyle {
     pub underline: bool,
     /// Strikethrough formatting
     pub strikethrough: bool,
+    /// Superscript formatting
+    pub superscript: bool,
+    /// Subscript formatting
+    pub subscript: bool,
     /// Text color (RGB)
     pub color: Option<(u8, u8, u8)>,
 }
@@ -33,10 +39,55 @@ impl TextStyle {
             || self.italic
             || self.underline
             || self.strikethrough
+            || self.superscript
+            || self.subscript
             || self.font_family.is_some()
             || self.font_size.is_some()
             || self.color.is_some()
     }
+
+    /// Apply the properties set in a TSWP.CharacterStylePropertiesArchive
+    ///
+    /// Properties the archive leaves unset keep their current value, so
+    /// applying a style chain from the root style to the most specific one
+    /// yields the effective formatting.
+    pub fn apply(&mut self, properties: &tswp::CharacterStylePropertiesArchive) {
+        use tswp::character_style_properties_archive::{
+            StrikethruType, SuperscriptType, UnderlineType,
+        };
+
+        if let Some(bold) = properties.bold {
+            self.bold = bold;
+        }
+        if let Some(italic) = properties.italic {
+            self.italic = italic;
+        }
+        if let Some(size) = properties.font_size {
+            self.font_size = Some(size);
+        }
+        if properties.font_name_null == Some(true) {
+            self.font_family = None;
+        } else if let Some(name) = &properties.font_name {
+            self.font_family = Some(name.clone());
+        }
+        if properties.font_color_null == Some(true) {
+            self.color = None;
+        } else if let Some(color) = &properties.font_color {
+            let channel =
+                |value: Option<f32>| (value.unwrap_or(0.0).clamp(0.0, 1.0) * 255.0).round() as u8;
+            self.color = Some((channel(color.r), channel(color.g), channel(color.b)));
+        }
+        if let Some(underline) = properties.underline {
+            self.underline = underline != UnderlineType::KNoUnderline as i32;
+        }
+        if let Some(strikethru) = properties.strikethru {
+            self.strikethrough = strikethru != StrikethruType::KNoStrikethru as i32;
+        }
+        if let Some(script) = properties.superscript {
+            self.superscript = script == SuperscriptType::KSuperscript as i32;
+            self.subscript = script == SuperscriptType::KSubscript as i32;
+        }
+    }
 }
 
 /// Paragraph style properties
@@ -111,6 +162,32 @@ mod tests {
         assert!(styled.has_formatting());
     }
 
+    #[test]
+    fn test_apply_character_properties() {
+        let mut style = TextStyle::new();
+        style.apply(&tswp::CharacterStylePropertiesArchive {
+            bold: Some(true),
+            font_size: Some(12.0),
+            superscript: Some(1),
+            ..Default::default()
+        });
+        style.apply(&tswp::CharacterStylePropertiesArchive {
+            italic: Some(true),
+            underline: Some(1),
+            ..Default::default()
+        });
+        assert!(style.bold && style.italic && style.underline && style.superscript);
+        assert!(!style.subscript && !style.strikethrough);
+        assert_eq!(style.font_size, Some(12.0));
+
+        style.apply(&tswp::CharacterStylePropertiesArchive {
+            bold: Some(false),
+            superscript: Some(0),
+            ..Default::default()
+        });
+        assert!(!style.bold && !style.superscript);
+    }
+
     #[test]
     fn test_paragraph_style() {
         let para = ParagraphStyle::new();
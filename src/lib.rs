@@ -178,6 +178,19 @@ pub mod common;
 ))]
 pub mod document;
 
+/// Format-agnostic entry point
+///
+/// [`open()`] and [`open_from_bytes()`] return an [`OfficeFile`] for any
+/// supported document, presentation or spreadsheet format.
+#[cfg(any(
+    feature = "ole",
+    feature = "ooxml",
+    feature = "rtf",
+    feature = "odf",
+    feature = "iwa"
+))]
+pub mod office;
+
 /// Image processing and conversion module
 ///
 /// Provides functionality to parse and convert Office Drawing formats
@@ -212,6 +225,36 @@ pub mod sheet;
 /// Use the [`markdown::ToMarkdown`] trait on Document or Presentation types.
 pub mod markdown;
 
+/// HTML export module
+///
+/// Converts presentations to reveal.js or plain HTML decks with
+/// [`Presentation::to_html()`], keeping the position and size of each shape.
+///
+/// **Note**: This requires the `html` feature to be enabled.
+#[cfg(all(
+    feature = "html",
+    any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa")
+))]
+pub mod html;
+
+/// PDF export module
+///
+/// Converts Word documents to PDF with [`Document::to_pdf()`], embedding a
+/// subset of a system font.
+///
+/// **Note**: This requires the `pdf` feature to be enabled.
+#[cfg(all(
+    feature = "pdf",
+    any(
+        feature = "ole",
+        feature = "ooxml",
+        feature = "rtf",
+        feature = "odf",
+        feature = "iwa"
+    )
+))]
+pub mod pdf;
+
 // Low-level format-specific modules (advanced use)
 /// OLE2 format parser (legacy .doc, .ppt files)
 ///
@@ -292,11 +335,22 @@ pub use common::{Error, Result};
 ))]
 pub use document::{Document, DocumentElement};
 
+#[cfg(any(
+    feature = "ole",
+    feature = "ooxml",
+    feature = "rtf",
+    feature = "odf",
+    feature = "iwa"
+))]
+pub use office::{OfficeFile, TextExtract, open, open_from_bytes};
+#[cfg(feature = "ooxml_encryption")]
+pub use office::{open_from_bytes_with_password, open_with_password};
+
 #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
 pub use presentation::Presentation;
 
 // Re-export commonly used types
 pub use common::{
-    FileFormat, Length, PlaceholderType, RGBColor, ShapeType, detect_file_format,
+    FileFormat, Length, OpenOptions, PlaceholderType, RGBColor, ShapeType, detect_file_format,
     detect_file_format_from_bytes,
 };
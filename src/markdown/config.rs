@@ -2,6 +2,9 @@
 ///
 /// This module defines the configuration options and enums used to customize
 /// the Markdown conversion process.
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
 /// Configuration options for Markdown conversion.
 ///
 /// This struct controls various aspects of the Markdown output, including
@@ -23,9 +26,8 @@
 /// ```
 ///
 /// # Note
-/// This struct is `Copy` since it only contains simple values,
-/// making it cheap to pass around without cloning overhead.
-#[derive(Debug, Copy, Clone)]
+/// Cloning is cheap: the image directory is shared behind an `Arc`.
+#[derive(Debug, Clone)]
 pub struct MarkdownOptions {
     /// Whether to include text styles (bold, italic, underline, etc.)
     pub include_styles: bool,
@@ -43,6 +45,16 @@ pub struct MarkdownOptions {
     pub script_style: ScriptStyle,
     /// How to render strikethrough text
     pub strikethrough_style: StrikethroughStyle,
+    /// Whether to render footnotes and endnotes as `[^1]` references
+    pub include_footnotes: bool,
+    /// Whether to render section headers and footers around the content
+    pub include_headers_footers: bool,
+    /// Whether to render text boxes and shape text after their anchor paragraphs
+    pub include_floating_text: bool,
+    /// How to render pictures
+    pub image_handling: ImageHandling,
+    /// Directory to extract pictures into, linked from the output
+    pub image_dir: Option<Arc<Path>>,
     /// Whether to use parallel processing for large documents (default: true)
     ///
     /// When enabled, uses rayon to process paragraphs in parallel for documents
@@ -62,6 +74,11 @@ impl Default for MarkdownOptions {
             list_indent: 2,
             script_style: ScriptStyle::Html,
             strikethrough_style: StrikethroughStyle::Markdown,
+            include_footnotes: false,
+            include_headers_footers: false,
+            include_floating_text: true,
+            image_handling: ImageHandling::Skip,
+            image_dir: None,
             use_parallel: true, // Enable parallel processing by default
         }
     }
@@ -216,6 +233,113 @@ impl MarkdownOptions {
         self
     }
 
+    /// Set whether to render footnotes and endnotes.
+    ///
+    /// When enabled, a `[^1]` reference is appended to the paragraph holding
+    /// each note's reference mark, and the note definitions are listed at the
+    /// end of the output. Endnote labels are prefixed with `e` (`[^e1]`).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::markdown::MarkdownOptions;
+    ///
+    /// let options = MarkdownOptions::new().with_footnotes(true);
+    /// ```
+    #[inline]
+    pub fn with_footnotes(mut self, include: bool) -> Self {
+        self.include_footnotes = include;
+        self
+    }
+
+    /// Set whether to render headers and footers.
+    ///
+    /// When enabled, the headers of each section are written once before the
+    /// document content and the footers once after it, instead of repeating
+    /// them for every page. Sections sharing the same header or footer
+    /// text produce it only once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::markdown::MarkdownOptions;
+    ///
+    /// let options = MarkdownOptions::new().with_headers_footers(true);
+    /// ```
+    #[inline]
+    pub fn with_headers_footers(mut self, include: bool) -> Self {
+        self.include_headers_footers = include;
+        self
+    }
+
+    /// Set whether to render text boxes and shape text.
+    ///
+    /// When enabled (the default), the text of each text box or shape is
+    /// written as a block quote after the paragraph it is anchored to, or
+    /// after the table holding that paragraph. See
+    /// [`Document::floating_text`](crate::Document::floating_text).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::markdown::MarkdownOptions;
+    ///
+    /// let options = MarkdownOptions::new().with_floating_text(false);
+    /// ```
+    #[inline]
+    pub fn with_floating_text(mut self, include: bool) -> Self {
+        self.include_floating_text = include;
+        self
+    }
+
+    /// Set how pictures are rendered.
+    ///
+    /// With [`ImageHandling::Placeholder`], an `![alt](image_1.png)` reference
+    /// is appended to the paragraph holding each picture, numbering the
+    /// pictures in document order. Pictures inside a table are listed in a
+    /// paragraph after the table. The file names match the order of
+    /// [`Document::images`](crate::Document::images), so the pictures can be
+    /// written out next to the Markdown.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::markdown::{ImageHandling, MarkdownOptions};
+    ///
+    /// let options = MarkdownOptions::new().with_images(ImageHandling::Placeholder);
+    /// ```
+    #[inline]
+    pub fn with_images(mut self, handling: ImageHandling) -> Self {
+        self.image_handling = handling;
+        self
+    }
+
+    /// Extract pictures into a directory and link them from the output.
+    ///
+    /// Each picture is written to `dir` as `image_001.png`, `image_002.jpeg`
+    /// and so on, and referenced with an `![alt](dir/image_001.png)` link
+    /// built from `dir` as given, so pass a path relative to where the
+    /// Markdown will be saved. Identical pictures, such as a logo repeated
+    /// on every page, are written once. With the `imgconv` feature, EMF,
+    /// WMF, PICT and DIB pictures are converted to PNG; otherwise they are
+    /// copied in their original format. The directory is created if needed.
+    ///
+    /// This implies [`ImageHandling::Placeholder`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::markdown::MarkdownOptions;
+    ///
+    /// let options = MarkdownOptions::new().with_image_dir("images");
+    /// ```
+    #[inline]
+    pub fn with_image_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.image_dir = Some(Arc::from(dir.into()));
+        self.image_handling = ImageHandling::Placeholder;
+        self
+    }
+
     /// Set whether to use parallel processing.
     ///
     /// When enabled, uses rayon to process paragraphs in parallel for large documents.
@@ -245,7 +369,8 @@ impl MarkdownOptions {
 /// Table rendering styles for Markdown conversion.
 ///
 /// **Note**: If the `TableStyle` is either `MinimalHtml` or `StyledHtml`,
-/// the formatted text inside the cells will **always** be rendered as HTML styled text.
+/// the formatted text inside the cells will **always** be rendered as HTML styled text,
+/// and right-to-left paragraphs are wrapped in `<div dir="rtl">` blocks.
 ///
 /// Determines how tables are rendered in the output.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -346,6 +471,20 @@ pub enum StrikethroughStyle {
     Html,
 }
 
+/// Picture rendering styles.
+///
+/// Determines whether pictures appear in the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageHandling {
+    /// Leave pictures out.
+    Skip,
+
+    /// Reference each picture by a numbered file name, using its alt text.
+    ///
+    /// Example: ![Company logo](image_1.png)
+    Placeholder,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -360,7 +499,10 @@ mod tests {
             .with_formula_style(FormulaStyle::Dollar)
             .with_list_indent(4)
             .with_script_style(ScriptStyle::Unicode)
-            .with_strikethrough_style(StrikethroughStyle::Html);
+            .with_strikethrough_style(StrikethroughStyle::Html)
+            .with_footnotes(true)
+            .with_floating_text(false)
+            .with_images(ImageHandling::Placeholder);
 
         assert!(options.include_styles);
         assert!(!options.include_metadata);
@@ -370,6 +512,9 @@ mod tests {
         assert_eq!(options.list_indent, 4);
         assert_eq!(options.script_style, ScriptStyle::Unicode);
         assert_eq!(options.strikethrough_style, StrikethroughStyle::Html);
+        assert!(options.include_footnotes);
+        assert!(!options.include_floating_text);
+        assert_eq!(options.image_handling, ImageHandling::Placeholder);
     }
 
     #[test]
@@ -383,5 +528,8 @@ mod tests {
         assert_eq!(options.list_indent, 2);
         assert_eq!(options.script_style, ScriptStyle::Html);
         assert_eq!(options.strikethrough_style, StrikethroughStyle::Markdown);
+        assert!(!options.include_footnotes);
+        assert!(options.include_floating_text);
+        assert_eq!(options.image_handling, ImageHandling::Skip);
     }
 }
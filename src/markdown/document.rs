@@ -1,4 +1,4 @@
-use super::config::MarkdownOptions;
+use super::config::{ImageHandling, MarkdownOptions};
 use super::traits::ToMarkdown;
 use super::writer::MarkdownWriter;
 /// ToMarkdown implementations for Document types.
@@ -8,8 +8,11 @@ use super::writer::MarkdownWriter;
 ///
 /// **Note**: This module is only available when the `ole` or `ooxml` feature is enabled.
 use crate::common::Result;
-use crate::document::{Document, Paragraph, Run, Table};
+use crate::document::{Document, HeaderFooter, ImageRef, Paragraph, Run, Table, displayable};
 use rayon::prelude::*;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
 
 /// Minimum number of elements to justify parallel processing overhead.
 const PARALLEL_THRESHOLD: usize = 50;
@@ -20,7 +23,7 @@ impl ToMarkdown for Document {
 
         // Write metadata first (must be sequential)
         let metadata_md = if options.include_metadata {
-            let mut metadata_writer = MarkdownWriter::new(*options);
+            let mut metadata_writer = MarkdownWriter::new(options.clone());
             let metadata = self.metadata()?;
             metadata_writer.write_metadata(&metadata)?;
             metadata_writer.finish()
@@ -28,17 +31,68 @@ impl ToMarkdown for Document {
             String::new()
         };
 
+        // Section headers and footers surrounding the content
+        let (headers_md, footers_md) = if options.include_headers_footers {
+            (
+                render_headers_footers(&self.headers()?, options, true)?,
+                render_headers_footers(&self.footers()?, options, false)?,
+            )
+        } else {
+            (String::new(), String::new())
+        };
+
+        // Note references keyed by paragraph index, and the note definitions
+        let (note_refs, notes_md) = if options.include_footnotes {
+            render_notes(self, options)?
+        } else {
+            (HashMap::new(), String::new())
+        };
+
+        // Picture placeholders keyed by paragraph index
+        let image_refs = match options.image_handling {
+            ImageHandling::Placeholder => render_image_placeholders(self, options)?,
+            ImageHandling::Skip => HashMap::new(),
+        };
+
+        // Text boxes as block quotes keyed by paragraph index
+        #[cfg_attr(not(feature = "ooxml"), allow(unused_mut))]
+        let mut floating_md = if options.include_floating_text {
+            render_floating_text(self, options)?
+        } else {
+            HashMap::new()
+        };
+
+        // SmartArt diagrams as nested lists, ahead of the text boxes of the
+        // same paragraph
+        #[cfg(feature = "ooxml")]
+        for (index, md) in render_smartart(self, options)? {
+            floating_md.entry(index).or_default().insert_str(0, &md);
+        }
+
         // Extract all document elements (paragraphs and tables) in document order
         let elements = self.elements()?;
 
+        // Paragraph index of each element, matching `Note::paragraph_index`,
+        // `ImageRef::paragraph_index` and `FloatingText::paragraph_index`
+        let mut para_indices = Vec::with_capacity(elements.len());
+        let mut next = 0;
+        for element in &elements {
+            para_indices.push(next);
+            next += match element {
+                DocumentElement::Paragraph(_) => 1,
+                DocumentElement::Table(table) => table.paragraph_count()?,
+            };
+        }
+
         // Decide whether to use parallel or sequential processing
         let content_md = if options.use_parallel && elements.len() >= PARALLEL_THRESHOLD {
             // PARALLEL PATH: Process elements in parallel for large documents
             // With Arc-based Send + Sync types, we can now safely parallelize
             let element_strings: Vec<String> = elements
                 .par_iter()
-                .map(|element| {
-                    let mut writer = MarkdownWriter::new(*options);
+                .zip(para_indices.par_iter())
+                .map(|(element, index)| {
+                    let mut writer = MarkdownWriter::new(options.clone());
                     match element {
                         DocumentElement::Paragraph(para) => {
                             let _ = writer.write_paragraph(para);
@@ -47,7 +101,19 @@ impl ToMarkdown for Document {
                             let _ = writer.write_table(table);
                         },
                     }
-                    writer.finish()
+                    let mut md = writer.finish();
+                    match element {
+                        DocumentElement::Paragraph(_) => {
+                            append_refs(&mut md, image_refs.get(index), note_refs.get(index));
+                            md.push_str(floating_md.get(index).map_or("", String::as_str));
+                        },
+                        DocumentElement::Table(table) => {
+                            let count = table.paragraph_count().unwrap_or(0);
+                            md.push_str(&table_image_refs(&image_refs, *index, count));
+                            md.push_str(&table_floating_text(&floating_md, *index, count));
+                        },
+                    }
+                    md
                 })
                 .collect();
 
@@ -64,18 +130,33 @@ impl ToMarkdown for Document {
         } else {
             // SEQUENTIAL PATH: Process elements sequentially for small documents
             // This avoids the parallelization overhead when it's not beneficial
-            let mut writer = MarkdownWriter::new(*options);
+            let mut writer = MarkdownWriter::new(options.clone());
             // Estimate: 100 bytes per paragraph, 500 bytes per table
             let estimated_size = elements.len() * 150; // Rough average
             writer.reserve(estimated_size);
 
-            for element in elements {
+            for (element, index) in elements.into_iter().zip(para_indices) {
                 match element {
                     DocumentElement::Paragraph(para) => {
-                        writer.write_paragraph(&para)?;
+                        match (image_refs.get(&index), note_refs.get(&index)) {
+                            (None, None) => writer.write_paragraph(&para)?,
+                            (images, notes) => {
+                                let mut para_writer = MarkdownWriter::new(options.clone());
+                                para_writer.write_paragraph(&para)?;
+                                let mut md = para_writer.finish();
+                                append_refs(&mut md, images, notes);
+                                writer.push_str(&md);
+                            },
+                        }
+                        if let Some(md) = floating_md.get(&index) {
+                            writer.push_str(md);
+                        }
                     },
                     DocumentElement::Table(table) => {
                         writer.write_table(&table)?;
+                        let count = table.paragraph_count()?;
+                        writer.push_str(&table_image_refs(&image_refs, index, count));
+                        writer.push_str(&table_floating_text(&floating_md, index, count));
                     },
                 }
             }
@@ -83,14 +164,290 @@ impl ToMarkdown for Document {
             writer.finish()
         };
 
-        // Combine metadata and content
-        Ok(format!("{}{}", metadata_md, content_md))
+        // Combine metadata, headers, content, note definitions and footers
+        Ok(format!(
+            "{}{}{}{}{}",
+            metadata_md, headers_md, content_md, notes_md, footers_md
+        ))
+    }
+}
+
+/// Render footnotes and endnotes as `[^label]` references and definitions.
+///
+/// Returns the references to append to each paragraph (by paragraph index)
+/// and the definition block that goes at the end of the document.
+fn render_notes(
+    doc: &Document,
+    options: &MarkdownOptions,
+) -> Result<(HashMap<usize, String>, String)> {
+    let mut refs: HashMap<usize, String> = HashMap::new();
+    let mut definitions = String::new();
+
+    for (prefix, notes) in [("", doc.footnotes()?), ("e", doc.endnotes()?)] {
+        for note in notes {
+            let label = format!("[^{}{}]", prefix, note.id());
+            if let Some(index) = note.paragraph_index() {
+                refs.entry(index).or_default().push_str(&label);
+            }
+
+            let paragraphs = note
+                .paragraphs()
+                .iter()
+                .map(|para| para.to_markdown_with_options(options))
+                .collect::<Result<Vec<_>>>()?;
+            // Continuation paragraphs of a note are indented by four spaces
+            let body = paragraphs
+                .join("\n\n    ")
+                .trim_matches(|c: char| c.is_whitespace() || c.is_control())
+                .to_string();
+
+            if definitions.is_empty() {
+                definitions.push('\n');
+            }
+            definitions.push_str(&format!("{}: {}\n", label, body));
+        }
+    }
+
+    Ok((refs, definitions))
+}
+
+/// Render pictures as `![alt](target)` references keyed by paragraph index.
+///
+/// Targets are `image_N.ext` names numbered in document order or, with an
+/// image directory, links to the extracted files. Pictures whose data cannot
+/// be read are left out when extracting.
+fn render_image_placeholders(
+    doc: &Document,
+    options: &MarkdownOptions,
+) -> Result<HashMap<usize, String>> {
+    let mut refs: HashMap<usize, String> = HashMap::new();
+    let mut writer = options.image_dir.as_deref().map(ImageWriter::new);
+
+    for (image, number) in doc.images()?.iter().zip(1..) {
+        let target = match writer.as_mut() {
+            Some(writer) => match writer.write(doc, image)? {
+                Some(link) => link,
+                None => continue,
+            },
+            None => format!("image_{}.{}", number, image.extension().unwrap_or("bin")),
+        };
+
+        let placeholder = refs.entry(image.paragraph_index()).or_default();
+        if !placeholder.is_empty() {
+            placeholder.push(' ');
+        }
+        placeholder.push_str("![");
+        for c in image.alt_text().unwrap_or_default().chars() {
+            match c {
+                '[' | ']' | '\\' => {
+                    placeholder.push('\\');
+                    placeholder.push(c);
+                },
+                c if c.is_control() => placeholder.push(' '),
+                c => placeholder.push(c),
+            }
+        }
+        placeholder.push_str(&format!("]({})", target));
+    }
+    Ok(refs)
+}
+
+/// Writes pictures into the image directory, once per distinct content.
+struct ImageWriter<'a> {
+    dir: &'a Path,
+    /// Link of the file written for each picture content hash
+    files: HashMap<[u8; 32], String>,
+}
+
+impl<'a> ImageWriter<'a> {
+    fn new(dir: &'a Path) -> Self {
+        Self {
+            dir,
+            files: HashMap::new(),
+        }
+    }
+
+    /// Write a picture unless identical data was already written, returning
+    /// the link to its file.
+    fn write(&mut self, doc: &Document, image: &ImageRef) -> Result<Option<String>> {
+        let Ok(data) = doc.image_data(image) else {
+            return Ok(None);
+        };
+        let hash: [u8; 32] = Sha256::digest(&data).into();
+        if let Some(link) = self.files.get(&hash) {
+            return Ok(Some(link.clone()));
+        }
+
+        let (data, extension) = displayable(data, image.extension().unwrap_or("bin"));
+        if self.files.is_empty() {
+            std::fs::create_dir_all(self.dir)?;
+        }
+        let name = format!("image_{:03}.{}", self.files.len() + 1, extension);
+        std::fs::write(self.dir.join(&name), data)?;
+
+        let mut link = self.dir.join(&name).to_string_lossy().into_owned();
+        if cfg!(windows) {
+            link = link.replace('\\', "/");
+        }
+        // Destinations with spaces or parentheses must be wrapped in <>
+        if link.contains([' ', '(', ')']) {
+            link = format!("<{}>", link);
+        }
+        self.files.insert(hash, link.clone());
+        Ok(Some(link))
+    }
+}
+
+/// Placeholders of the pictures inside a table, as a paragraph following it.
+fn table_image_refs(image_refs: &HashMap<usize, String>, start: usize, count: usize) -> String {
+    let refs: Vec<&str> = (start..start + count)
+        .filter_map(|index| image_refs.get(&index).map(String::as_str))
+        .collect();
+    if refs.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n\n", refs.join(" "))
+    }
+}
+
+/// Render text boxes and shape text as block quotes keyed by paragraph index.
+///
+/// Each shape becomes its own block quote, written after the paragraph it is
+/// anchored to. Shapes without text are left out.
+fn render_floating_text(
+    doc: &Document,
+    options: &MarkdownOptions,
+) -> Result<HashMap<usize, String>> {
+    let mut blocks: HashMap<usize, String> = HashMap::new();
+
+    for shape in doc.floating_text()? {
+        let body = shape
+            .paragraphs()
+            .iter()
+            .map(|para| para.to_markdown_with_options(options))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(|md| {
+                md.trim_matches(|c: char| c.is_whitespace() || c.is_control())
+                    .to_string()
+            })
+            .filter(|md| !md.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        if body.is_empty() {
+            continue;
+        }
+
+        let block = blocks.entry(shape.paragraph_index()).or_default();
+        for line in body.lines() {
+            if line.is_empty() {
+                block.push_str(">\n");
+            } else {
+                block.push_str("> ");
+                block.push_str(line);
+                block.push('\n');
+            }
+        }
+        block.push('\n');
+    }
+
+    Ok(blocks)
+}
+
+/// Render SmartArt diagrams as nested lists keyed by paragraph index.
+#[cfg(feature = "ooxml")]
+fn render_smartart(doc: &Document, options: &MarkdownOptions) -> Result<HashMap<usize, String>> {
+    let mut blocks: HashMap<usize, String> = HashMap::new();
+    for (index, smartart) in doc.smartart_by_paragraph()? {
+        let mut writer = MarkdownWriter::new(options.clone());
+        writer.write_smartart(&smartart)?;
+        blocks.entry(index).or_default().push_str(&writer.finish());
+    }
+    Ok(blocks)
+}
+
+/// Block quotes of the shapes anchored inside a table, following it.
+fn table_floating_text(floating: &HashMap<usize, String>, start: usize, count: usize) -> String {
+    (start..start + count)
+        .filter_map(|index| floating.get(&index).map(String::as_str))
+        .collect()
+}
+
+/// Render section headers or footers, each distinct block once.
+///
+/// Headers are followed and footers preceded by a thematic break (`---`)
+/// separating them from the document content.
+fn render_headers_footers(
+    parts: &[HeaderFooter],
+    options: &MarkdownOptions,
+    headers: bool,
+) -> Result<String> {
+    let mut blocks: Vec<String> = Vec::new();
+    for part in parts {
+        let block = part
+            .paragraphs()
+            .iter()
+            .map(|para| para.to_markdown_with_options(options))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .map(|md| {
+                md.trim_matches(|c: char| c.is_whitespace() || c.is_control())
+                    .to_string()
+            })
+            .filter(|md| !md.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        // Sections usually repeat the same header, so keep each block once
+        if !block.is_empty() && !blocks.contains(&block) {
+            blocks.push(block);
+        }
+    }
+
+    if blocks.is_empty() {
+        return Ok(String::new());
+    }
+    let body = blocks.join("\n\n");
+    Ok(if headers {
+        format!("{}\n\n---\n\n", body)
+    } else {
+        format!("\n---\n\n{}\n", body)
+    })
+}
+
+/// Insert picture placeholders and note references at the end of a rendered
+/// paragraph, before its line breaks.
+fn append_refs(md: &mut String, images: Option<&String>, notes: Option<&String>) {
+    let mut end = md.trim_end().len();
+    // References of right-to-left paragraphs go inside their `<div>` block
+    if md.starts_with("<div dir=\"rtl\">")
+        && let Some(content) = md[..end].strip_suffix("</div>")
+    {
+        end = content.trim_end().len();
+    }
+    let mut refs = String::new();
+    if let Some(images) = images {
+        // Keep placeholders apart from the paragraph text
+        if end > 0 {
+            refs.push(' ');
+        }
+        refs.push_str(images);
+    }
+    if let Some(notes) = notes {
+        refs.push_str(notes);
+    }
+    if end == 0 {
+        // Pictures are often alone in an otherwise empty paragraph
+        md.clear();
+        md.push_str(&refs);
+        md.push_str("\n\n");
+    } else {
+        md.insert_str(end, &refs);
     }
 }
 
 impl ToMarkdown for Paragraph {
     fn to_markdown_with_options(&self, options: &MarkdownOptions) -> Result<String> {
-        let mut writer = MarkdownWriter::new(*options);
+        let mut writer = MarkdownWriter::new(options.clone());
         writer.write_paragraph(self)?;
         Ok(writer.finish().trim_end().to_string())
     }
@@ -98,7 +455,7 @@ impl ToMarkdown for Paragraph {
 
 impl ToMarkdown for Run {
     fn to_markdown_with_options(&self, options: &MarkdownOptions) -> Result<String> {
-        let mut writer = MarkdownWriter::new(*options);
+        let mut writer = MarkdownWriter::new(options.clone());
         writer.write_run(self)?;
         Ok(writer.finish())
     }
@@ -106,7 +463,7 @@ impl ToMarkdown for Run {
 
 impl ToMarkdown for Table {
     fn to_markdown_with_options(&self, options: &MarkdownOptions) -> Result<String> {
-        let mut writer = MarkdownWriter::new(*options);
+        let mut writer = MarkdownWriter::new(options.clone());
         writer.write_table(self)?;
         Ok(writer.finish().trim_end().to_string())
     }
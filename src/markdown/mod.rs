@@ -118,5 +118,7 @@ mod document;
 mod presentation;
 
 // Re-export public API
-pub use config::{FormulaStyle, MarkdownOptions, ScriptStyle, StrikethroughStyle, TableStyle};
+pub use config::{
+    FormulaStyle, ImageHandling, MarkdownOptions, ScriptStyle, StrikethroughStyle, TableStyle,
+};
 pub use traits::ToMarkdown;
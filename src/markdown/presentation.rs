@@ -8,7 +8,7 @@ use super::writer::MarkdownWriter;
 ///
 /// **Note**: This module is only available when the `ole` or `ooxml` feature is enabled.
 use crate::common::Result;
-use crate::presentation::{Presentation, Slide};
+use crate::presentation::{Presentation, Slide, SlideContent};
 use rayon::prelude::*;
 
 /// Minimum number of slides to justify parallel processing overhead.
@@ -20,7 +20,7 @@ impl ToMarkdown for Presentation {
         let metadata_md = if options.include_metadata
             && let Some(metadata) = self.metadata()?
         {
-            let mut metadata_writer = MarkdownWriter::new(*options);
+            let mut metadata_writer = MarkdownWriter::new(options.clone());
             metadata_writer.write_metadata(&metadata)?;
             metadata_writer.finish()
         } else {
@@ -29,7 +29,7 @@ impl ToMarkdown for Presentation {
 
         // Use optimized fast path that extracts text without shape parsing
         // This is significantly faster for PPT files (3-10x speedup)
-        let slide_texts = self.extract_text_for_markdown()?;
+        let slide_texts = self.extract_content_for_markdown()?;
 
         // Decide whether to use parallel or sequential processing
         let content_md = if options.use_parallel && slide_texts.len() >= PARALLEL_THRESHOLD {
@@ -37,29 +37,12 @@ impl ToMarkdown for Presentation {
             let slide_count = slide_texts.len();
             let slide_strings: Vec<String> = slide_texts
                 .into_par_iter()
-                .map(|(slide_num, text)| {
-                    let mut writer = MarkdownWriter::new(*options);
-
-                    // Format slide header with first line as title
-                    let first_line = text.lines().next().unwrap_or("");
-                    let header_text = if first_line.is_empty() {
-                        format!("# Slide {}", slide_num)
-                    } else {
-                        format!("# Slide {} {}", slide_num, first_line)
-                    };
-
-                    writer.push_str(&header_text);
-                    writer.push_str("\n\n");
-
-                    // Add slide content
-                    if !text.is_empty() {
-                        writer.push_str(&text);
-                        writer.push_str("\n\n");
-                    }
-
-                    writer.finish()
+                .map(|slide| {
+                    let mut writer = MarkdownWriter::new(options.clone());
+                    write_slide(&mut writer, &slide)?;
+                    Ok(writer.finish())
                 })
-                .collect();
+                .collect::<Result<_>>()?;
 
             // Estimate total size and pre-allocate
             let total_size: usize = slide_strings.iter().map(|s| s.len()).sum();
@@ -77,29 +60,13 @@ impl ToMarkdown for Presentation {
             result
         } else {
             // SEQUENTIAL PATH: Process slides sequentially for small presentations
-            let mut writer = MarkdownWriter::new(*options);
+            let mut writer = MarkdownWriter::new(options.clone());
 
-            for (i, (slide_num, text)) in slide_texts.iter().enumerate() {
+            for (i, slide) in slide_texts.iter().enumerate() {
                 if i > 0 {
                     writer.push_str("\n\n---\n\n");
                 }
-
-                // Format slide header with first line as title
-                let first_line = text.lines().next().unwrap_or("");
-                let header_text = if first_line.is_empty() {
-                    format!("# Slide {}", slide_num)
-                } else {
-                    format!("# Slide {} {}", slide_num, first_line)
-                };
-
-                writer.push_str(&header_text);
-                writer.push_str("\n\n");
-
-                // Add slide content
-                if !text.is_empty() {
-                    writer.push_str(text);
-                    writer.push_str("\n\n");
-                }
+                write_slide(&mut writer, slide)?;
             }
 
             writer.finish()
@@ -111,9 +78,61 @@ impl ToMarkdown for Presentation {
 }
 
 impl ToMarkdown for Slide {
-    fn to_markdown_with_options(&self, _options: &MarkdownOptions) -> Result<String> {
-        // For individual slides, just return the text
+    fn to_markdown_with_options(&self, options: &MarkdownOptions) -> Result<String> {
+        // For individual slides, return the text followed by the tables
         // Formatting is minimal for presentations
-        self.text()
+        let tables = self.tables()?;
+        #[cfg(feature = "ooxml")]
+        let smartart = self.smartart()?;
+        #[cfg(feature = "ooxml")]
+        let plain = tables.is_empty() && smartart.is_empty();
+        #[cfg(not(feature = "ooxml"))]
+        let plain = tables.is_empty();
+        if plain {
+            return self.text();
+        }
+
+        let mut writer = MarkdownWriter::new(options.clone());
+        let text = self.text_without_tables()?;
+        if !text.is_empty() {
+            writer.push_str(&text);
+            writer.push_str("\n\n");
+        }
+        #[cfg(feature = "ooxml")]
+        for diagram in &smartart {
+            writer.write_smartart(diagram)?;
+        }
+        for table in &tables {
+            writer.write_slide_table(table)?;
+        }
+        Ok(writer.finish())
+    }
+}
+
+/// Write a slide of a presentation: a header titled with the first line of
+/// the slide text, then the text, SmartArt diagrams and tables.
+fn write_slide(writer: &mut MarkdownWriter, slide: &SlideContent) -> Result<()> {
+    let first_line = slide.text.lines().next().unwrap_or("");
+    let header_text = if first_line.is_empty() {
+        format!("# Slide {}", slide.number)
+    } else {
+        format!("# Slide {} {}", slide.number, first_line)
+    };
+
+    writer.push_str(&header_text);
+    writer.push_str("\n\n");
+
+    // Add slide content
+    if !slide.text.is_empty() {
+        writer.push_str(&slide.text);
+        writer.push_str("\n\n");
+    }
+    #[cfg(feature = "ooxml")]
+    for diagram in &slide.smartart {
+        writer.write_smartart(diagram)?;
+    }
+    for table in &slide.tables {
+        writer.write_slide_table(table)?;
     }
+    Ok(())
 }
@@ -6,7 +6,7 @@ use super::config::{MarkdownOptions, TableStyle};
 ///
 /// **Note**: Some functionality requires the `ole` or `ooxml` feature to be enabled.
 use crate::common::{Error, Metadata, Result};
-use crate::document::{Cell, Paragraph, Run, Table};
+use crate::document::{Cell, DocumentElement, ListInfo, Paragraph, Run, Table};
 use memchr::memchr;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use std::fmt::Write as FmtWrite;
@@ -83,30 +83,23 @@ pub(crate) struct MarkdownWriter {
     current_strikethrough: bool,
 }
 
-/// Pre-extracted cell information for efficient table processing.
-///
-/// This struct caches cell span data to avoid repeated parsing during span analysis.
-/// Text content is extracted separately for better performance.
-#[derive(Debug, Clone)]
-struct CellData {
-    /// Horizontal span (gridSpan/colspan)
-    grid_span: usize,
-    /// Vertical merge state (OOXML only)
-    #[cfg(feature = "ooxml")]
-    v_merge: Option<crate::ooxml::docx::VMergeState>,
-}
+/// HTML to render in each table row, with the span information for every grid cell.
+#[cfg(any(
+    feature = "ole",
+    feature = "ooxml",
+    feature = "odf",
+    feature = "rtf",
+    feature = "iwa"
+))]
+type TableLayout = (Vec<Vec<String>>, Vec<Vec<CellSpan>>);
 
-/// Analyze a table to compute cell spans (colspan/rowspan) for proper HTML rendering.
-///
-/// This function processes a table and computes the actual colspan and rowspan for each cell,
-/// taking into account:
-/// - `gridSpan` (horizontal merge/colspan)
-/// - `vMerge` (vertical merge/rowspan)
+/// Lay a table out on its grid for HTML rendering.
 ///
-/// Returns a 2D vector where `result[row][col]` contains the span information for that cell.
-///
-/// **Performance**: Optimized to extract all cell data in a single pass, avoiding repeated
-/// parsing. For large tables, uses parallel processing to extract cell data concurrently.
+/// `contents` holds the HTML of every physical cell. Returns the HTML to
+/// render in each row together with the span information for every grid
+/// cell. Cells that continue a merge are left out, and grid positions no cell
+/// covers (such as those before a row's first cell) become empty cells so the
+/// columns stay aligned.
 #[cfg(any(
     feature = "ole",
     feature = "ooxml",
@@ -114,138 +107,69 @@ struct CellData {
     feature = "rtf",
     feature = "iwa"
 ))]
-fn analyze_table_spans(table: &Table, use_parallel: bool) -> Result<Vec<Vec<CellSpan>>> {
-    let rows = table.rows()?;
-    if rows.is_empty() {
-        return Ok(Vec::new());
+fn layout_table(table: &Table, texts: Vec<Vec<String>>) -> Result<TableLayout> {
+    let placements = table.cell_placements()?;
+    let row_count = texts.len();
+    let column_count = placements
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|p| p.column + p.column_span)
+        .max()
+        .unwrap_or(0);
+
+    let mut spans = vec![vec![CellSpan::new(); column_count]; row_count];
+    let mut starts: Vec<Vec<Option<String>>> = vec![vec![None; column_count]; row_count];
+
+    for (row_texts, row_placements) in texts.into_iter().zip(&placements) {
+        for (text, placement) in row_texts.into_iter().zip(row_placements) {
+            let Some(p) = placement else { continue };
+            let row_end = (p.row + p.row_span).min(row_count);
+            for row in &mut spans[p.row..row_end] {
+                row[p.column..p.column + p.column_span].fill(CellSpan::skipped());
+            }
+            spans[p.row][p.column] = CellSpan {
+                colspan: p.column_span,
+                rowspan: row_end - p.row,
+                skip: false,
+            };
+            starts[p.row][p.column] = Some(text);
+        }
     }
 
-    // OPTIMIZATION: Pre-extract all cell data in a single pass to avoid repeated parsing.
-    // This is the key optimization - we parse each cell exactly once.
-    // For large tables, use parallel processing.
-    let cell_data: Result<Vec<Vec<CellData>>> =
-        if use_parallel && rows.len() > TABLE_PARALLEL_THRESHOLD {
-            // PARALLEL PATH: Extract cell data in parallel for large tables
-            // First collect all cells to avoid borrowing issues
-            let all_cells: Result<Vec<Vec<Cell>>> = rows.iter().map(|row| row.cells()).collect();
-            let all_cells = all_cells?;
-
-            all_cells
-                .par_iter()
-                .map(|cells| {
-                    cells
-                        .iter()
-                        .map(|cell| {
-                            Ok(CellData {
-                                grid_span: cell.grid_span().unwrap_or(1),
-                                #[cfg(feature = "ooxml")]
-                                v_merge: cell.v_merge().ok().flatten(),
-                            })
-                        })
-                        .collect()
-                })
+    let cell_data = starts
+        .into_iter()
+        .zip(&spans)
+        .map(|(row_starts, row_spans)| {
+            row_starts
+                .into_iter()
+                .zip(row_spans)
+                .filter(|(_, span)| !span.skip)
+                .map(|(text, _)| text.unwrap_or_default())
                 .collect()
-        } else {
-            // SEQUENTIAL PATH: Extract cell data sequentially for small tables
-            rows.iter()
-                .map(|row| {
-                    let cells = row.cells()?;
-                    cells
-                        .iter()
-                        .map(|cell| {
-                            Ok(CellData {
-                                grid_span: cell.grid_span().unwrap_or(1),
-                                #[cfg(feature = "ooxml")]
-                                v_merge: cell.v_merge().ok().flatten(),
-                            })
-                        })
-                        .collect()
-                })
-                .collect()
-        };
-    let cell_data = cell_data?;
-
-    // First pass: determine the maximum grid width (considering gridSpan)
-    let mut max_grid_cols = 0;
-    for row_cells in &cell_data {
-        let row_grid_cols: usize = row_cells.iter().map(|c| c.grid_span).sum();
-        max_grid_cols = max_grid_cols.max(row_grid_cols);
-    }
-
-    // Initialize span info for all cells
-    let mut spans: Vec<Vec<CellSpan>> = vec![vec![CellSpan::new(); max_grid_cols]; rows.len()];
+        })
+        .collect();
 
-    // Second pass: analyze gridSpan and vMerge for each cell
-    for (row_idx, row_cells) in cell_data.iter().enumerate() {
-        let mut grid_col = 0; // Current grid column position
-
-        for cell in row_cells {
-            // Skip grid columns that are covered by previous cells' colspan
-            while grid_col < max_grid_cols && spans[row_idx][grid_col].skip {
-                grid_col += 1;
-            }
-
-            if grid_col >= max_grid_cols {
-                break;
-            }
-
-            // Get horizontal span (gridSpan)
-            let colspan = cell.grid_span;
-            spans[row_idx][grid_col].colspan = colspan;
-
-            // Mark columns covered by this cell's colspan as skipped
-            for offset in 1..colspan {
-                if grid_col + offset < max_grid_cols {
-                    spans[row_idx][grid_col + offset] = CellSpan::skipped();
-                }
-            }
+    Ok((cell_data, spans))
+}
 
-            // Get vertical merge state (vMerge)
-            #[cfg(feature = "ooxml")]
-            {
-                use crate::ooxml::docx::VMergeState;
-
-                if let Some(v_merge_state) = &cell.v_merge {
-                    match v_merge_state {
-                        VMergeState::Restart => {
-                            // This cell starts a vertical merge
-                            // Count how many rows below continue this merge
-                            let mut rowspan = 1;
-                            for next_row_idx in (row_idx + 1)..cell_data.len() {
-                                if let Some(next_cell) = cell_data[next_row_idx].get(grid_col) {
-                                    if matches!(next_cell.v_merge, Some(VMergeState::Continue)) {
-                                        rowspan += 1;
-                                        // Mark this cell as skipped
-                                        spans[next_row_idx][grid_col] = CellSpan::skipped();
-                                        // Also mark colspan cells as skipped
-                                        for offset in 1..colspan {
-                                            if grid_col + offset < max_grid_cols {
-                                                spans[next_row_idx][grid_col + offset] =
-                                                    CellSpan::skipped();
-                                            }
-                                        }
-                                    } else {
-                                        break;
-                                    }
-                                } else {
-                                    break;
-                                }
-                            }
-                            spans[row_idx][grid_col].rowspan = rowspan;
-                        },
-                        VMergeState::Continue => {
-                            // This cell continues a merge from above, should be skipped
-                            // (already marked in the Restart case above)
-                        },
-                    }
-                }
+/// Check if any cell of a table holds a nested table.
+#[cfg(any(
+    feature = "ole",
+    feature = "ooxml",
+    feature = "odf",
+    feature = "rtf",
+    feature = "iwa"
+))]
+fn table_has_nested_tables(table: &Table) -> Result<bool> {
+    for row in table.rows()? {
+        for cell in row.cells()? {
+            if !cell.tables()?.is_empty() {
+                return Ok(true);
             }
-
-            grid_col += colspan;
         }
     }
-
-    Ok(spans)
+    Ok(false)
 }
 
 /// Extract all cell data from a table in a single optimized pass.
@@ -260,6 +184,24 @@ fn analyze_table_spans(table: &Table, use_parallel: bool) -> Result<Vec<Vec<Cell
     feature = "iwa"
 ))]
 fn extract_table_cell_data(table: &Table, use_parallel: bool) -> Result<Vec<Vec<String>>> {
+    map_table_cells(table, use_parallel, Cell::text)
+}
+
+/// Apply `f` to every cell of a table, row by row.
+///
+/// **Performance**: For large tables, uses parallel processing to visit rows concurrently.
+#[cfg(any(
+    feature = "ole",
+    feature = "ooxml",
+    feature = "odf",
+    feature = "rtf",
+    feature = "iwa"
+))]
+fn map_table_cells<T, F>(table: &Table, use_parallel: bool, f: F) -> Result<Vec<Vec<T>>>
+where
+    T: Send,
+    F: Fn(&Cell) -> Result<T> + Sync,
+{
     let rows = table.rows()?;
     if rows.is_empty() {
         return Ok(Vec::new());
@@ -272,17 +214,17 @@ fn extract_table_cell_data(table: &Table, use_parallel: bool) -> Result<Vec<Vec<
         let all_cells: Result<Vec<Vec<Cell>>> = rows.iter().map(|row| row.cells()).collect();
         let all_cells = all_cells?;
 
-        // Now extract texts in parallel
+        // Now visit the cells in parallel
         all_cells
             .par_iter()
-            .map(|cells| cells.iter().map(|cell| cell.text()).collect())
+            .map(|cells| cells.iter().map(&f).collect())
             .collect()
     } else {
         // Sequential extraction for small tables
         rows.iter()
             .map(|row| {
                 let cells = row.cells()?;
-                cells.iter().map(|cell| cell.text()).collect()
+                cells.iter().map(&f).collect()
             })
             .collect()
     }
@@ -302,7 +244,41 @@ impl MarkdownWriter {
 
     /// Write a paragraph to the buffer.
     ///
+    /// Markdown has no syntax for text direction, so when tables are
+    /// written as HTML ([`TableStyle::MinimalHtml`] or
+    /// [`TableStyle::StyledHtml`]), right-to-left paragraphs are wrapped in
+    /// a `<div dir="rtl">` block. With [`TableStyle::Markdown`] the output
+    /// stays free of HTML and the text is left in logical order.
+    ///
     /// **Note**: This method requires the `ole` or `ooxml` feature to be enabled.
+    #[cfg(any(
+        feature = "ole",
+        feature = "ooxml",
+        feature = "odf",
+        feature = "rtf",
+        feature = "iwa"
+    ))]
+    pub fn write_paragraph(&mut self, para: &Paragraph) -> Result<()> {
+        if self.options.table_style == TableStyle::Markdown || !para.direction()?.is_rtl() {
+            return self.write_paragraph_content(para);
+        }
+
+        // Write the content on its own first, since empty paragraphs get no block
+        let outer = std::mem::take(&mut self.buffer);
+        let result = self.write_paragraph_content(para);
+        let content = std::mem::replace(&mut self.buffer, outer);
+        result?;
+        if content.trim().is_empty() {
+            self.buffer.push_str(&content);
+        } else {
+            self.buffer.push_str("<div dir=\"rtl\">\n\n");
+            self.buffer.push_str(&content);
+            self.buffer.push_str("</div>\n\n");
+        }
+        Ok(())
+    }
+
+    /// Write the content of a paragraph, followed by a blank line.
     ///
     /// **Performance**: Optimized to avoid redundant XML parsing by extracting runs
     /// once and deriving text from them when needed.
@@ -314,21 +290,30 @@ impl MarkdownWriter {
         feature = "iwa"
     ))]
     #[allow(irrefutable_let_patterns)]
-    pub fn write_paragraph(&mut self, para: &Paragraph) -> Result<()> {
-        // First check for paragraph-level formulas (display math)
+    fn write_paragraph_content(&mut self, para: &Paragraph) -> Result<()> {
+        // Paragraphs the document itself numbers carry their list info
+        if let Some(list) = para.list_info() {
+            self.write_numbered_paragraph(para, list)?;
+            self.close_formatting();
+            self.buffer.push_str("\n\n");
+            return Ok(());
+        }
+
+        // Equations are runs of their own; plain text would lose them, so
+        // write the runs in order with each equation converted to LaTeX
         #[cfg(feature = "ooxml")]
+        if !self.options.include_styles
+            && let Paragraph::Docx(docx_para) = para
+            && docx_para.has_math()
         {
-            use crate::document::Paragraph;
-            if let Paragraph::Docx(docx_para) = para {
-                let display_formulas = docx_para.paragraph_level_formulas()?;
-                if !display_formulas.is_empty() {
-                    // This paragraph contains display formulas
-                    // Process runs and formulas together in order
-                    self.write_paragraph_with_display_formulas(para, display_formulas)?;
-                    self.buffer.push_str("\n\n");
-                    return Ok(());
+            for run in para.runs()? {
+                match self.extract_formula_from_run(&run)? {
+                    Some(formula) => self.buffer.push_str(&formula),
+                    None => self.buffer.push_str(&run.text()?),
                 }
             }
+            self.buffer.push_str("\n\n");
+            return Ok(());
         }
 
         // PERFORMANCE OPTIMIZATION:
@@ -433,72 +418,33 @@ impl MarkdownWriter {
         Ok(())
     }
 
-    /// Write a paragraph that contains display-level formulas.
+    /// Write a paragraph numbered by the document's list definitions.
     ///
-    /// This handles paragraphs where formulas are direct children of the paragraph (not within runs).
-    #[cfg(all(feature = "ooxml", feature = "formula"))]
-    fn write_paragraph_with_display_formulas(
-        &mut self,
-        para: &Paragraph,
-        display_formulas: Vec<String>,
-    ) -> Result<()> {
-        use crate::formula::omml_to_latex;
-
-        // For display formulas, we'll write each formula on its own line
-        // and interleave with any text content from runs
-        let runs = para.runs()?;
-
-        // Write all runs first (if any)
-        for run in runs {
-            let text = run.text()?;
-            if !text.trim().is_empty() {
-                self.buffer.push_str(&text);
+    /// Uses the level for indentation instead of guessing from the text.
+    /// Numbers such as `"3."` or `"3)"` are kept; other labels (letters,
+    /// Roman numerals, multi-level numbers) become `1.` and let the
+    /// Markdown renderer count.
+    fn write_numbered_paragraph(&mut self, para: &Paragraph, list: &ListInfo) -> Result<()> {
+        let indent = " ".repeat(list.level() as usize * self.options.list_indent);
+        let marker = if list.is_ordered() {
+            let number = list.number_text().trim().trim_end_matches(['.', ')']);
+            if !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit()) {
+                format!("{}.", number)
+            } else {
+                "1.".to_string()
             }
-        }
-
-        // Add line break if there was text before formulas
-        if !self.buffer.ends_with("\n\n") && !self.buffer.ends_with('\n') {
-            self.buffer.push('\n');
-        }
-
-        // Write display formulas
-        for omml_xml in display_formulas {
-            let latex = match omml_to_latex(&omml_xml) {
-                Ok(l) => l,
-                Err(_) => "[Formula conversion error]".to_string(),
-            };
-
-            // Display formulas use display style (false = display mode)
-            let formula_md = self.format_formula(&latex, false);
-            self.buffer.push_str(&formula_md);
-            self.buffer.push('\n');
-        }
-
-        Ok(())
-    }
+        } else {
+            "-".to_string()
+        };
+        write!(self.buffer, "{}{} ", indent, marker).map_err(|e| Error::Other(e.to_string()))?;
 
-    /// Fallback for when formula feature is not enabled.
-    #[cfg(all(feature = "ooxml", not(feature = "formula")))]
-    fn write_paragraph_with_display_formulas(
-        &mut self,
-        para: &Paragraph,
-        display_formulas: Vec<String>,
-    ) -> Result<()> {
-        // Write runs normally
-        let runs = para.runs()?;
-        for run in runs {
-            let text = run.text()?;
-            if !text.trim().is_empty() {
-                self.buffer.push_str(&text);
+        if self.options.include_styles {
+            for run in para.runs()? {
+                self.write_run(&run)?;
             }
+        } else {
+            self.buffer.push_str(para.text()?.trim_start());
         }
-
-        // Add placeholder for formulas
-        for _ in display_formulas {
-            self.buffer
-                .push_str("\n[Formula - enable 'formula' feature]\n");
-        }
-
         Ok(())
     }
 
@@ -794,11 +740,11 @@ impl MarkdownWriter {
         feature = "iwa"
     ))]
     pub fn write_table(&mut self, table: &Table) -> Result<()> {
-        // Check if table has merged cells
-        let has_merged_cells = self.table_has_merged_cells(table)?;
+        // Markdown tables cannot express merged cells or nested tables
+        let needs_html = self.table_has_merged_cells(table)? || table_has_nested_tables(table)?;
 
         match self.options.table_style {
-            TableStyle::Markdown if !has_merged_cells => {
+            TableStyle::Markdown if !needs_html => {
                 self.write_markdown_table(table)?;
             },
             TableStyle::MinimalHtml | TableStyle::Markdown => {
@@ -814,13 +760,104 @@ impl MarkdownWriter {
         Ok(())
     }
 
-    /// Check if a table has merged cells.
+    /// Write a presentation slide table to the buffer.
     ///
-    /// Uses proper span analysis to detect merged cells by checking for:
-    /// - Horizontal merges (gridSpan/colspan > 1)
-    /// - Vertical merges (vMerge/rowspan > 1)
+    /// Rendered like [`Self::write_table`], following the configured
+    /// [`TableStyle`]: tables with merged cells fall back to HTML.
+    #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+    pub fn write_slide_table(&mut self, table: &crate::presentation::SlideTable) -> Result<()> {
+        let cell_data: Vec<Vec<String>> = table
+            .rows()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .filter(|cell| !cell.is_merged())
+                    .map(|cell| cell.text().to_string())
+                    .collect()
+            })
+            .collect();
+
+        match self.options.table_style {
+            TableStyle::Markdown if !table.has_merged_cells() => {
+                self.write_markdown_rows(&cell_data);
+            },
+            style => {
+                let cell_data: Vec<Vec<String>> = cell_data
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|text| {
+                                let mut html = String::with_capacity(text.len());
+                                Self::escape_html_to_buffer(&mut html, text);
+                                html
+                            })
+                            .collect()
+                    })
+                    .collect();
+                let spans: Vec<Vec<CellSpan>> = table
+                    .rows()
+                    .iter()
+                    .map(|row| {
+                        row.iter()
+                            .map(|cell| CellSpan {
+                                colspan: cell.col_span(),
+                                rowspan: cell.row_span(),
+                                skip: cell.is_merged(),
+                            })
+                            .collect()
+                    })
+                    .collect();
+                self.write_html_rows(&cell_data, &spans, style == TableStyle::StyledHtml);
+            },
+        }
+
+        // Add spacing after table
+        self.buffer.push_str("\n\n");
+        Ok(())
+    }
+
+    /// Write a SmartArt diagram as a bulleted list nested like its nodes.
     ///
-    /// **Performance**: Efficient analysis that reuses existing span computation.
+    /// Nodes without text are left out and their children take their place.
+    #[cfg(feature = "ooxml")]
+    pub fn write_smartart(&mut self, smartart: &crate::ooxml::pptx::SmartArt) -> Result<()> {
+        fn write_nodes(
+            buffer: &mut String,
+            nodes: &[crate::ooxml::pptx::DiagramNode],
+            level: usize,
+            indent: usize,
+        ) {
+            for node in nodes {
+                let text = node.text.split_whitespace().collect::<Vec<_>>().join(" ");
+                if text.is_empty() {
+                    write_nodes(buffer, &node.children, level, indent);
+                    continue;
+                }
+                buffer.push_str(&" ".repeat(level * indent));
+                buffer.push_str("- ");
+                buffer.push_str(&text);
+                buffer.push('\n');
+                write_nodes(buffer, &node.children, level + 1, indent);
+            }
+        }
+
+        let start = self.buffer.len();
+        write_nodes(
+            &mut self.buffer,
+            smartart.nodes(),
+            0,
+            self.options.list_indent,
+        );
+        if self.buffer.len() > start {
+            self.buffer.push('\n');
+        }
+        Ok(())
+    }
+
+    /// Check if a table has merged cells.
+    ///
+    /// A table has merged cells when a cell covers more than one grid
+    /// position or continues a merge started by another cell.
     #[cfg(any(
         feature = "ole",
         feature = "ooxml",
@@ -829,31 +866,11 @@ impl MarkdownWriter {
         feature = "iwa"
     ))]
     fn table_has_merged_cells(&self, table: &Table) -> Result<bool> {
-        let rows = table.rows()?;
-        if rows.is_empty() {
-            return Ok(false);
-        }
-
-        // Quick check: Look for cells with gridSpan > 1 or vMerge attributes
-        for row in &rows {
-            let cells = row.cells()?;
-            for cell in &cells {
-                // Check horizontal merge (gridSpan)
-                if cell.grid_span().unwrap_or(1) > 1 {
-                    return Ok(true);
-                }
-
-                // Check vertical merge (vMerge) - only available for OOXML
-                #[cfg(feature = "ooxml")]
-                {
-                    if cell.v_merge().ok().flatten().is_some() {
-                        return Ok(true);
-                    }
-                }
-            }
-        }
-
-        Ok(false)
+        Ok(table
+            .cell_placements()?
+            .iter()
+            .flatten()
+            .any(|placement| placement.is_none_or(|p| p.column_span > 1 || p.row_span > 1)))
     }
 
     /// Write a table in Markdown format.
@@ -871,8 +888,21 @@ impl MarkdownWriter {
     fn write_markdown_table(&mut self, table: &Table) -> Result<()> {
         // OPTIMIZATION: Extract all cell data in a single pass (with parallelization for large tables)
         let cell_data = extract_table_cell_data(table, self.options.use_parallel)?;
+        self.write_markdown_rows(&cell_data);
+        Ok(())
+    }
+
+    /// Write rows of cell texts as a Markdown table, the first row being the header.
+    #[cfg(any(
+        feature = "ole",
+        feature = "ooxml",
+        feature = "odf",
+        feature = "rtf",
+        feature = "iwa"
+    ))]
+    fn write_markdown_rows(&mut self, cell_data: &[Vec<String>]) {
         if cell_data.is_empty() {
-            return Ok(());
+            return;
         }
 
         // Pre-allocate buffer capacity
@@ -935,8 +965,6 @@ impl MarkdownWriter {
                 self.buffer.push('\n');
             }
         }
-
-        Ok(())
     }
 
     /// Write markdown-escaped text (escape | and convert \n to space) directly to buffer.
@@ -1027,21 +1055,102 @@ impl MarkdownWriter {
         feature = "iwa"
     ))]
     fn write_html_table(&mut self, table: &Table, styled: bool) -> Result<()> {
-        // OPTIMIZATION: Extract all cell data in a single pass (with parallelization for large tables)
-        let cell_data = extract_table_cell_data(table, self.options.use_parallel)?;
+        // Render all cells in a single pass and place them on the grid
+        let contents = map_table_cells(table, self.options.use_parallel, |cell| {
+            self.cell_html(cell)
+        })?;
+        let (cell_data, spans) = layout_table(table, contents)?;
         if cell_data.is_empty() {
             return Ok(());
         }
 
+        self.write_html_rows(&cell_data, &spans, styled);
+        Ok(())
+    }
+
+    /// Render the content of a table cell as HTML.
+    ///
+    /// Tables nested in the cell become HTML tables in place, between the
+    /// cell's non-empty paragraphs. Right-to-left paragraphs are wrapped in
+    /// `<div dir="rtl">`.
+    #[cfg(any(
+        feature = "ole",
+        feature = "ooxml",
+        feature = "odf",
+        feature = "rtf",
+        feature = "iwa"
+    ))]
+    fn cell_html(&self, cell: &Cell) -> Result<String> {
+        let mut html = String::new();
+        if cell.tables()?.is_empty() {
+            let mut rtl = false;
+            for para in cell.paragraphs()? {
+                rtl |= para.direction()?.is_rtl();
+            }
+            if !rtl {
+                Self::escape_html_to_buffer(&mut html, &cell.text()?);
+                return Ok(html);
+            }
+        }
+
+        let mut after_text = false;
+        for element in cell.elements()? {
+            match element {
+                DocumentElement::Paragraph(para) => {
+                    let text = para.text()?;
+                    if text.is_empty() {
+                        continue;
+                    }
+                    if para.direction()?.is_rtl() {
+                        html.push_str("<div dir=\"rtl\">");
+                        Self::escape_html_to_buffer(&mut html, &text);
+                        html.push_str("</div>");
+                        after_text = false;
+                        continue;
+                    }
+                    if after_text {
+                        html.push_str("<br>");
+                    }
+                    Self::escape_html_to_buffer(&mut html, &text);
+                    after_text = true;
+                },
+                DocumentElement::Table(table) => {
+                    let mut writer = MarkdownWriter::new(self.options.clone());
+                    writer.write_html_table(&table, false)?;
+                    html.push_str(&writer.finish());
+                    after_text = false;
+                },
+            }
+        }
+        Ok(html)
+    }
+
+    /// Write rows of cells as an HTML table.
+    ///
+    /// `cell_data` holds the HTML of the cells that are not covered by a
+    /// merge, and `spans` the span information for every grid cell.
+    #[cfg(any(
+        feature = "ole",
+        feature = "ooxml",
+        feature = "odf",
+        feature = "rtf",
+        feature = "iwa"
+    ))]
+    fn write_html_rows(
+        &mut self,
+        cell_data: &[Vec<String>],
+        spans: &[Vec<CellSpan>],
+        styled: bool,
+    ) {
+        if cell_data.is_empty() {
+            return;
+        }
+
         // Pre-allocate buffer capacity to reduce reallocations
         // Estimate: ~100 bytes per cell on average
         let total_cells: usize = cell_data.iter().map(|row| row.len()).sum();
         self.buffer.reserve(total_cells * 100);
 
-        // Analyze table to get span information (colspan/rowspan)
-        // Use the same parallel setting as for cell extraction
-        let spans = analyze_table_spans(table, self.options.use_parallel)?;
-
         // Helper to format a single cell
         let format_cell =
             |text: &str, tag: &str, span: &CellSpan, cell_indent: Option<&str>| -> String {
@@ -1068,8 +1177,7 @@ impl MarkdownWriter {
 
                 cell_buffer.push('>');
 
-                // HTML escape and write text
-                Self::escape_html_to_buffer(&mut cell_buffer, text);
+                cell_buffer.push_str(text);
 
                 // Write closing tag
                 cell_buffer.push_str("</");
@@ -1150,7 +1258,7 @@ impl MarkdownWriter {
                             row_texts,
                             row_idx,
                             tag,
-                            &spans,
+                            spans,
                             Some(&double_indent),
                         ));
                         row_html.push_str(&indent);
@@ -1176,7 +1284,7 @@ impl MarkdownWriter {
                         row_texts,
                         row_idx,
                         tag,
-                        &spans,
+                        spans,
                         Some(&double_indent),
                     ));
                     self.buffer.push_str(&indent);
@@ -1199,7 +1307,7 @@ impl MarkdownWriter {
                         let tag = if row_idx == 0 { "th" } else { "td" };
                         let mut row_html = String::with_capacity(row_texts.len() * 100 + 20);
                         row_html.push_str("<tr>");
-                        row_html.push_str(&format_row(row_texts, row_idx, tag, &spans, None));
+                        row_html.push_str(&format_row(row_texts, row_idx, tag, spans, None));
                         row_html.push_str("</tr>");
                         row_html
                     })
@@ -1218,15 +1326,13 @@ impl MarkdownWriter {
 
                     self.buffer.push_str("<tr>");
                     self.buffer
-                        .push_str(&format_row(row_texts, row_idx, tag, &spans, None));
+                        .push_str(&format_row(row_texts, row_idx, tag, spans, None));
                     self.buffer.push_str("</tr>");
                 }
             }
 
             self.buffer.push_str("</table>");
         }
-
-        Ok(())
     }
 
     /// Helper function to escape HTML to a string buffer.
@@ -1424,6 +1530,9 @@ impl MarkdownWriter {
     /// Extract formula content from a run and convert to markdown.
     ///
     /// Returns the markdown representation of the formula if one is found, None otherwise.
+    /// OMML equations and MTEF (MathType) objects are both parsed into a formula
+    /// tree and written as LaTeX; only docx equations wrapped in `<m:oMathPara>`
+    /// are display math.
     #[cfg(any(
         feature = "ole",
         feature = "ooxml",
@@ -1433,7 +1542,30 @@ impl MarkdownWriter {
     ))]
     #[allow(irrefutable_let_patterns)]
     fn extract_formula_from_run(&self, _run: &Run) -> Result<Option<String>> {
-        // Try OOXML OMML formulas first
+        // Try OOXML equations first
+        #[cfg(feature = "ooxml")]
+        if let crate::document::Run::Docx(docx_run) = _run
+            && docx_run.is_math()
+        {
+            let inline = !docx_run.is_display_math();
+
+            #[cfg(feature = "formula")]
+            {
+                let formula = crate::formula::Formula::new();
+                let latex = match docx_run.math(formula.arena()) {
+                    Ok(Some(nodes)) => self.convert_math_to_latex(&nodes),
+                    _ => "[Formula conversion error]".to_string(),
+                };
+                return Ok(Some(self.format_formula(&latex, inline)));
+            }
+
+            #[cfg(not(feature = "formula"))]
+            return Ok(Some(
+                self.format_formula("[Formula - enable 'formula' feature]", inline),
+            ));
+        }
+
+        // Equations embedded inside a <w:r> by other producers
         #[cfg(feature = "ooxml")]
         if let crate::document::Run::Docx(docx_run) = _run
             && let Some(omml_xml) = docx_run.omml_formula()?
@@ -1468,7 +1600,7 @@ impl MarkdownWriter {
                 // Get the MTEF formula AST
                 if let Some(mtef_ast) = ole_run.mtef_formula_ast() {
                     // Convert MTEF AST to LaTeX
-                    let latex = self.convert_mtef_to_latex(mtef_ast);
+                    let latex = self.convert_math_to_latex(mtef_ast);
                     return Ok(Some(self.format_formula(&latex, true))); // true = inline
                 } else {
                     // Fallback placeholder if AST is not available
@@ -1490,7 +1622,7 @@ impl MarkdownWriter {
                 // Get the MTEF formula AST
                 if let Some(mtef_ast) = ole_run.mtef_formula_ast() {
                     // Convert MTEF AST to LaTeX
-                    let latex = self.convert_mtef_to_latex(mtef_ast);
+                    let latex = self.convert_math_to_latex(mtef_ast);
                     return Ok(Some(self.format_formula(&latex, true))); // true = inline
                 } else {
                     // Fallback placeholder if AST is not available
@@ -1502,9 +1634,9 @@ impl MarkdownWriter {
         Ok(None)
     }
 
-    /// Convert MTEF AST nodes to LaTeX string
+    /// Convert formula AST nodes (from OMML or MTEF) to LaTeX string
     #[cfg(feature = "formula")]
-    fn convert_mtef_to_latex(&self, nodes: &[crate::formula::MathNode]) -> String {
+    fn convert_math_to_latex(&self, nodes: &[crate::formula::MathNode]) -> String {
         use crate::formula::latex::LatexConverter;
 
         let mut converter = LatexConverter::new();
@@ -1514,10 +1646,10 @@ impl MarkdownWriter {
         }
     }
 
-    /// Convert MTEF AST nodes to LaTeX string (fallback when formula feature is disabled)
+    /// Convert formula AST nodes to LaTeX string (fallback when formula feature is disabled)
     #[cfg(not(feature = "formula"))]
     #[allow(dead_code)]
-    fn convert_mtef_to_latex(&self, _nodes: &[()]) -> String {
+    fn convert_math_to_latex(&self, _nodes: &[()]) -> String {
         "[Formula support disabled - enable 'formula' feature]".to_string()
     }
 
@@ -0,0 +1,307 @@
+//! Decryption of password-protected ODF package entries.
+//!
+//! Every encrypted entry has its own salt and initialisation vector in the
+//! manifest. The entry was deflated, encrypted with a key that PBKDF2 derives
+//! from a digest of the password, and stored uncompressed in the ZIP archive.
+//! Two cipher suites are in common use:
+//!
+//! - Blowfish CFB with a SHA-1 start key and a SHA-1 checksum, written by
+//!   OpenOffice.org and old LibreOffice versions
+//! - AES-CBC (usually 256 bit) with a SHA-256 start key and a SHA-256
+//!   checksum, written by LibreOffice since 3.4
+//!
+//! # References
+//! - ODF 1.2 Part 3: §3.8 (Encryption) and §4.8 (`manifest:encryption-data`)
+//! - ODF 1.3 Part 2: §3.8 (additional checksum and start key algorithms)
+
+use super::manifest::{EncryptionData, ManifestEntry};
+use crate::common::{Error, Result};
+use aes::cipher::{AsyncStreamCipher, BlockDecryptMut, KeyIvInit, block_padding::NoPadding};
+use aes::{Aes128, Aes192, Aes256};
+use blowfish::Blowfish;
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+use std::io::Read;
+
+/// Number of leading bytes of the decrypted data covered by the checksum
+const CHECKSUM_LENGTH: usize = 1024;
+
+/// Namespace of the algorithm names added by ODF 1.3
+const MANIFEST_NS: &str = "urn:oasis:names:tc:opendocument:xmlns:manifest:1.0#";
+
+/// Decrypt and inflate an encrypted package entry.
+///
+/// The checksum of the manifest is verified before inflating, so a wrong
+/// password is reported as [`Error::IncorrectPassword`] and a damaged entry
+/// as [`Error::CorruptedFile`].
+pub(crate) fn decrypt_entry(
+    entry: &ManifestEntry,
+    encryption: &EncryptionData,
+    data: &[u8],
+    password: &str,
+) -> Result<Vec<u8>> {
+    let key = derive_key(encryption, password)?;
+    let (decrypted, padding_valid) = decrypt(entry, encryption, &key, data)?;
+
+    if !checksum_matches(encryption, &decrypted)? {
+        return Err(Error::IncorrectPassword(format!(
+            "the password does not decrypt {}",
+            entry.full_path
+        )));
+    }
+    if !padding_valid {
+        return Err(Error::CorruptedFile(format!(
+            "{} has invalid padding after decryption",
+            entry.full_path
+        )));
+    }
+
+    inflate(entry, decrypted)
+}
+
+/// Derive the key of an entry from the password.
+fn derive_key(encryption: &EncryptionData, password: &str) -> Result<Vec<u8>> {
+    let start_key = match algorithm_name(&encryption.start_key_generation) {
+        "SHA1" | "sha1" => Sha1::digest(password.as_bytes()).to_vec(),
+        "SHA256" | "sha256" => Sha256::digest(password.as_bytes()).to_vec(),
+        other => {
+            return Err(Error::UnsupportedEncryption(format!(
+                "start key generation {}",
+                other
+            )));
+        },
+    };
+
+    match algorithm_name(&encryption.key_derivation) {
+        "PBKDF2" | "pbkdf2" => {
+            let mut key = vec![0; encryption.key_size];
+            pbkdf2::pbkdf2_hmac::<Sha1>(
+                &start_key,
+                &encryption.salt,
+                encryption.iteration_count,
+                &mut key,
+            );
+            Ok(key)
+        },
+        other => Err(Error::UnsupportedEncryption(format!(
+            "key derivation {}",
+            other
+        ))),
+    }
+}
+
+/// Decrypt the data of an entry, returning the plain data and whether its
+/// padding was valid.
+fn decrypt(
+    entry: &ManifestEntry,
+    encryption: &EncryptionData,
+    key: &[u8],
+    data: &[u8],
+) -> Result<(Vec<u8>, bool)> {
+    let iv = &encryption.initialisation_vector;
+    let invalid = || {
+        Error::CorruptedFile(format!(
+            "{} has a key or initialisation vector of the wrong size for {}",
+            entry.full_path, encryption.algorithm
+        ))
+    };
+
+    match algorithm_name(&encryption.algorithm) {
+        "Blowfish CFB" | "blowfish" => {
+            let mut decrypted = data.to_vec();
+            cfb_mode::Decryptor::<Blowfish>::new_from_slices(key, iv)
+                .map_err(|_| invalid())?
+                .decrypt(&mut decrypted);
+            Ok((decrypted, true))
+        },
+        "aes128-cbc" => decrypt_cbc::<Aes128>(entry, key, iv, data).ok_or_else(invalid)?,
+        "aes192-cbc" => decrypt_cbc::<Aes192>(entry, key, iv, data).ok_or_else(invalid)?,
+        "aes256-cbc" => decrypt_cbc::<Aes256>(entry, key, iv, data).ok_or_else(invalid)?,
+        other => Err(Error::UnsupportedEncryption(format!("cipher {}", other))),
+    }
+}
+
+/// Decrypt AES-CBC data and remove its padding, or `None` if the key or
+/// initialisation vector has the wrong size.
+///
+/// The padding of XML Encryption is arbitrary bytes followed by the length
+/// of the padding, so only that last byte can be checked.
+fn decrypt_cbc<C>(
+    entry: &ManifestEntry,
+    key: &[u8],
+    iv: &[u8],
+    data: &[u8],
+) -> Option<Result<(Vec<u8>, bool)>>
+where
+    C: aes::cipher::BlockCipher + aes::cipher::BlockDecrypt + aes::cipher::KeyInit,
+{
+    let decryptor = cbc::Decryptor::<C>::new_from_slices(key, iv).ok()?;
+    let Ok(mut decrypted) = decryptor.decrypt_padded_vec_mut::<NoPadding>(data) else {
+        return Some(Err(Error::CorruptedFile(format!(
+            "{} is not a whole number of cipher blocks",
+            entry.full_path
+        ))));
+    };
+
+    let padding = decrypted.last().copied().unwrap_or(0) as usize;
+    let padding_valid = (1..=C::block_size()).contains(&padding) && padding <= decrypted.len();
+    if padding_valid {
+        decrypted.truncate(decrypted.len() - padding);
+    }
+    Some(Ok((decrypted, padding_valid)))
+}
+
+/// Whether the decrypted data matches the checksum of the manifest.
+///
+/// Entries without a checksum cannot be verified and are accepted.
+fn checksum_matches(encryption: &EncryptionData, decrypted: &[u8]) -> Result<bool> {
+    if encryption.checksum.is_empty() {
+        return Ok(true);
+    }
+
+    let start = &decrypted[..decrypted.len().min(CHECKSUM_LENGTH)];
+    let digest = match algorithm_name(&encryption.checksum_type) {
+        "SHA1/1K" | "sha1-1k" => Sha1::digest(start).to_vec(),
+        "SHA256/1K" | "sha256-1k" => Sha256::digest(start).to_vec(),
+        other => {
+            return Err(Error::UnsupportedEncryption(format!("checksum {}", other)));
+        },
+    };
+    Ok(digest == encryption.checksum)
+}
+
+/// Inflate decrypted data, unless the entry was stored without compression.
+///
+/// The ZIP archive lists encrypted entries as stored, so only the
+/// uncompressed size from the manifest tells whether the data was deflated
+/// before encryption. Already compressed media such as images are not.
+fn inflate(entry: &ManifestEntry, decrypted: Vec<u8>) -> Result<Vec<u8>> {
+    if entry.size == Some(decrypted.len() as u64) {
+        return Ok(decrypted);
+    }
+
+    let mut inflated = Vec::with_capacity(entry.size.unwrap_or(0) as usize);
+    match flate2::read::DeflateDecoder::new(decrypted.as_slice()).read_to_end(&mut inflated) {
+        Ok(_) if entry.size.is_some_and(|size| size != inflated.len() as u64) => {
+            Err(Error::CorruptedFile(format!(
+                "{} inflates to {} bytes instead of {}",
+                entry.full_path,
+                inflated.len(),
+                entry.size.unwrap_or(0)
+            )))
+        },
+        Ok(_) => Ok(inflated),
+        // Without a size, data that does not inflate may have been stored
+        Err(_) if entry.size.is_none() => Ok(decrypted),
+        Err(error) => Err(Error::CorruptedFile(format!(
+            "{} could not be inflated after decryption: {}",
+            entry.full_path, error
+        ))),
+    }
+}
+
+/// The short name of an algorithm, without the namespace of an ODF 1.3 or
+/// XML Encryption and Signature URI.
+fn algorithm_name(name: &str) -> &str {
+    if let Some(short) = name.strip_prefix(MANIFEST_NS) {
+        return short;
+    }
+    match name.rsplit_once('#') {
+        Some((namespace, short)) if namespace.starts_with("http://www.w3.org/") => short,
+        _ => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes::cipher::{BlockEncryptMut, block_padding::Pkcs7};
+
+    fn entry(size: usize) -> ManifestEntry {
+        ManifestEntry {
+            full_path: "content.xml".to_string(),
+            media_type: "text/xml".to_string(),
+            size: Some(size as u64),
+            encrypted: true,
+            encryption: None,
+        }
+    }
+
+    /// Deflate and encrypt `plain` with AES-256-CBC the way LibreOffice does
+    fn encrypt_aes(plain: &[u8], password: &str) -> (EncryptionData, Vec<u8>) {
+        use flate2::write::DeflateEncoder;
+        use std::io::Write;
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(plain).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let encryption = EncryptionData {
+            checksum_type: format!("{}sha256-1k", MANIFEST_NS),
+            checksum: Sha256::digest(&compressed[..compressed.len().min(1024)]).to_vec(),
+            algorithm: "http://www.w3.org/2001/04/xmlenc#aes256-cbc".to_string(),
+            initialisation_vector: vec![7; 16],
+            start_key_generation: "http://www.w3.org/2000/09/xmldsig#sha256".to_string(),
+            key_derivation: "PBKDF2".to_string(),
+            key_size: 32,
+            iteration_count: 10,
+            salt: vec![3; 16],
+        };
+        let key = derive_key(&encryption, password).unwrap();
+        let encrypted = cbc::Encryptor::<Aes256>::new_from_slices(&key, &[7; 16])
+            .unwrap()
+            .encrypt_padded_vec_mut::<Pkcs7>(&compressed);
+        (encryption, encrypted)
+    }
+
+    #[test]
+    fn test_algorithm_names() {
+        assert_eq!(
+            algorithm_name("http://www.w3.org/2001/04/xmlenc#aes256-cbc"),
+            "aes256-cbc"
+        );
+        assert_eq!(
+            algorithm_name("urn:oasis:names:tc:opendocument:xmlns:manifest:1.0#sha256-1k"),
+            "sha256-1k"
+        );
+        assert_eq!(algorithm_name("SHA1/1K"), "SHA1/1K");
+        assert_eq!(algorithm_name("Blowfish CFB"), "Blowfish CFB");
+    }
+
+    #[test]
+    fn test_decrypt_entry_round_trip() {
+        let plain = b"<office:document-content/>".repeat(100);
+        let (encryption, encrypted) = encrypt_aes(&plain, "secret");
+        let decrypted =
+            decrypt_entry(&entry(plain.len()), &encryption, &encrypted, "secret").unwrap();
+        assert_eq!(decrypted, plain);
+    }
+
+    #[test]
+    fn test_decrypt_entry_wrong_password() {
+        let plain = b"<office:document-content/>".to_vec();
+        let (encryption, encrypted) = encrypt_aes(&plain, "secret");
+        let result = decrypt_entry(&entry(plain.len()), &encryption, &encrypted, "guess");
+        assert!(matches!(result, Err(Error::IncorrectPassword(_))));
+    }
+
+    #[test]
+    fn test_decrypt_entry_corrupted_after_checksum() {
+        // Damage past the first 1024 bytes is not covered by the checksum
+        let plain: Vec<u8> = (0..20_000u32).flat_map(|i| i.to_le_bytes()).collect();
+        let (encryption, mut encrypted) = encrypt_aes(&plain, "secret");
+        let middle = encrypted.len() / 2 + 3;
+        encrypted[middle] ^= 0xFF;
+        let result = decrypt_entry(&entry(plain.len()), &encryption, &encrypted, "secret");
+        assert!(matches!(result, Err(Error::CorruptedFile(_))));
+    }
+
+    #[test]
+    fn test_unsupported_key_derivation() {
+        let (mut encryption, encrypted) = encrypt_aes(b"data", "secret");
+        encryption.key_derivation =
+            "urn:org:documentfoundation:names:experimental:office:manifest:argon2id".to_string();
+        let result = decrypt_entry(&entry(4), &encryption, &encrypted, "secret");
+        assert!(matches!(result, Err(Error::UnsupportedEncryption(_))));
+    }
+}
@@ -23,6 +23,49 @@ pub struct ManifestEntry {
     pub media_type: String,
     pub size: Option<u64>,
     pub encrypted: bool,
+    /// How the entry is encrypted, from its `manifest:encryption-data`
+    pub encryption: Option<EncryptionData>,
+}
+
+/// Encryption parameters of a password-protected manifest entry.
+///
+/// The key is derived from a digest of the password (the start key), and
+/// the checksum is a digest of the start of the decrypted, still compressed
+/// data, which tells a wrong password apart from a damaged entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptionData {
+    /// Digest used for the checksum, such as `SHA1/1K`
+    pub checksum_type: String,
+    pub checksum: Vec<u8>,
+    /// Cipher, such as `Blowfish CFB` or an XML Encryption AES URI
+    pub algorithm: String,
+    pub initialisation_vector: Vec<u8>,
+    /// Digest of the password used as the start key
+    pub start_key_generation: String,
+    /// Key derivation function, such as `PBKDF2`
+    pub key_derivation: String,
+    /// Size of the derived key in bytes
+    pub key_size: usize,
+    pub iteration_count: u32,
+    pub salt: Vec<u8>,
+}
+
+impl Default for EncryptionData {
+    /// The defaults of ODF 1.2 for the elements and attributes that may be
+    /// omitted: a SHA-1 start key and a 16 byte key.
+    fn default() -> Self {
+        Self {
+            checksum_type: String::new(),
+            checksum: Vec::new(),
+            algorithm: String::new(),
+            initialisation_vector: Vec::new(),
+            start_key_generation: "SHA1".to_string(),
+            key_derivation: String::new(),
+            key_size: 16,
+            iteration_count: 0,
+            salt: Vec::new(),
+        }
+    }
 }
 
 impl Manifest {
@@ -52,27 +95,49 @@ impl Manifest {
         let mut buf = Vec::new();
 
         let mut entries = HashMap::new();
+        // Entry whose children are being read
+        let mut current: Option<ManifestEntry> = None;
 
         loop {
             match reader.read_event_into(&mut buf) {
-                Ok(Event::Start(ref e)) => {
-                    if e.name().as_ref() == b"manifest:file-entry"
-                        && let Some(entry) = Self::parse_file_entry(e)?
-                    {
-                        let full_path = entry.full_path.clone();
-                        entries.insert(full_path, entry);
-                    }
+                Ok(Event::Start(ref e)) => match e.name().as_ref() {
+                    b"manifest:file-entry" => current = Self::parse_file_entry(e)?,
+                    name => {
+                        if let Some(entry) = current.as_mut() {
+                            Self::parse_encryption_element(name, e, entry)?;
+                        }
+                    },
+                },
+                Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                    b"manifest:file-entry" => {
+                        if let Some(entry) = Self::parse_file_entry(e)? {
+                            let full_path = entry.full_path.clone();
+                            entries.insert(full_path, entry);
+                        }
+                    },
+                    name => {
+                        if let Some(entry) = current.as_mut() {
+                            Self::parse_encryption_element(name, e, entry)?;
+                        }
+                    },
                 },
-                Ok(Event::Empty(ref e)) => {
+                Ok(Event::End(ref e)) => {
                     if e.name().as_ref() == b"manifest:file-entry"
-                        && let Some(entry) = Self::parse_file_entry(e)?
+                        && let Some(entry) = current.take()
                     {
                         let full_path = entry.full_path.clone();
                         entries.insert(full_path, entry);
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(Error::InvalidFormat(format!("XML parsing error: {}", e))),
+                Err(e) => {
+                    return Err(Error::xml_at(
+                        Some("META-INF/manifest.xml"),
+                        xml_content.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
+                },
                 _ => {},
             }
             buf.clear();
@@ -121,12 +186,72 @@ impl Manifest {
                 media_type,
                 size,
                 encrypted,
+                encryption: None,
             }))
         } else {
             Ok(None)
         }
     }
 
+    /// Parse `manifest:encryption-data` or one of its children into `entry`
+    fn parse_encryption_element(
+        name: &[u8],
+        e: &quick_xml::events::BytesStart,
+        entry: &mut ManifestEntry,
+    ) -> Result<()> {
+        if name == b"manifest:encryption-data" {
+            entry.encryption = Some(EncryptionData::default());
+            entry.encrypted = true;
+        }
+        let Some(encryption) = entry.encryption.as_mut() else {
+            return Ok(());
+        };
+
+        for attr_result in e.attributes() {
+            let attr = attr_result
+                .map_err(|_| Error::InvalidFormat("Invalid attribute in manifest".to_string()))?;
+            let value = String::from_utf8(attr.value.to_vec())
+                .map_err(|_| Error::InvalidFormat("Invalid UTF-8 in manifest".to_string()))?;
+
+            match (name, attr.key.as_ref()) {
+                (b"manifest:encryption-data", b"manifest:checksum-type") => {
+                    encryption.checksum_type = value
+                },
+                (b"manifest:encryption-data", b"manifest:checksum") => {
+                    encryption.checksum = decode_base64(&value)?
+                },
+                (b"manifest:algorithm", b"manifest:algorithm-name") => encryption.algorithm = value,
+                (b"manifest:algorithm", b"manifest:initialisation-vector") => {
+                    encryption.initialisation_vector = decode_base64(&value)?
+                },
+                (b"manifest:start-key-generation", b"manifest:start-key-generation-name") => {
+                    encryption.start_key_generation = value
+                },
+                (b"manifest:key-derivation", b"manifest:key-derivation-name") => {
+                    encryption.key_derivation = value
+                },
+                (b"manifest:key-derivation", b"manifest:key-size") => {
+                    encryption.key_size = value.parse().map_err(|_| {
+                        Error::InvalidFormat(format!("Invalid key size in manifest: {}", value))
+                    })?
+                },
+                (b"manifest:key-derivation", b"manifest:iteration-count") => {
+                    encryption.iteration_count = value.parse().map_err(|_| {
+                        Error::InvalidFormat(format!(
+                            "Invalid iteration count in manifest: {}",
+                            value
+                        ))
+                    })?
+                },
+                (b"manifest:key-derivation", b"manifest:salt") => {
+                    encryption.salt = decode_base64(&value)?
+                },
+                _ => {},
+            }
+        }
+        Ok(())
+    }
+
     /// Get media type for a path
     #[allow(dead_code)]
     pub fn get_media_type(&self, path: &str) -> Option<&str> {
@@ -154,6 +279,15 @@ impl Manifest {
     }
 }
 
+/// Decode a base64 attribute value of the manifest
+fn decode_base64(value: &str) -> Result<Vec<u8>> {
+    use base64::Engine as _;
+
+    base64::engine::general_purpose::STANDARD
+        .decode(value.trim())
+        .map_err(|_| Error::InvalidFormat(format!("Invalid base64 in manifest: {}", value)))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -232,4 +366,37 @@ mod tests {
         let entry = manifest.get_entry("content.xml").unwrap();
         assert!(entry.encrypted);
     }
+
+    #[test]
+    fn test_manifest_encryption_data() {
+        let xml = r#"<?xml version="1.0"?>
+<manifest:manifest xmlns:manifest="urn:oasis:names:tc:opendocument:xmlns:manifest:1.0">
+    <manifest:file-entry manifest:full-path="content.xml" manifest:media-type="text/xml" manifest:size="2798">
+        <manifest:encryption-data manifest:checksum-type="SHA1/1K" manifest:checksum="AAEC">
+            <manifest:algorithm manifest:algorithm-name="Blowfish CFB" manifest:initialisation-vector="AwQ="/>
+            <manifest:key-derivation manifest:key-derivation-name="PBKDF2" manifest:iteration-count="1024" manifest:salt="BQYH"/>
+        </manifest:encryption-data>
+    </manifest:file-entry>
+    <manifest:file-entry manifest:full-path="styles.xml" manifest:media-type="text/xml"/>
+</manifest:manifest>"#;
+        let manifest = Manifest::parse(xml).unwrap();
+
+        let entry = manifest.get_entry("content.xml").unwrap();
+        assert!(entry.encrypted);
+        assert_eq!(entry.size, Some(2798));
+        let encryption = entry.encryption.as_ref().unwrap();
+        assert_eq!(encryption.checksum_type, "SHA1/1K");
+        assert_eq!(encryption.checksum, [0, 1, 2]);
+        assert_eq!(encryption.algorithm, "Blowfish CFB");
+        assert_eq!(encryption.initialisation_vector, [3, 4]);
+        // Defaults of the omitted start key generation and key size
+        assert_eq!(encryption.start_key_generation, "SHA1");
+        assert_eq!(encryption.key_size, 16);
+        assert_eq!(encryption.iteration_count, 1024);
+        assert_eq!(encryption.salt, [5, 6, 7]);
+
+        let styles = manifest.get_entry("styles.xml").unwrap();
+        assert!(!styles.encrypted);
+        assert!(styles.encryption.is_none());
+    }
 }
@@ -1,12 +1,15 @@
 //! ODF metadata parsing functionality.
 //!
 //! This module provides comprehensive parsing of ODF metadata from meta.xml,
-//! including document properties, statistics, and user information.
+//! including document properties, statistics, and user information, and the
+//! meta.xml serialization shared by the ODF writers.
 
-use crate::common::{Error, Metadata, Result};
-use chrono::{DateTime, Utc};
+use crate::common::xml::escape_xml;
+use crate::common::{CustomPropertyValue, Error, Metadata, Result};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
 use quick_xml::events::Event;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::fmt::Write;
 
 /// Comprehensive ODF metadata
 #[derive(Debug, Clone, Default)]
@@ -29,10 +32,12 @@ pub struct OdfMetadata {
     pub modification_date: Option<String>,
     /// Generator application
     pub generator: Option<String>,
+    /// Number of editing cycles (times the document was saved)
+    pub editing_cycles: Option<u32>,
     /// Document statistics
     pub statistics: DocumentStatistics,
-    /// Custom properties
-    pub custom_properties: HashMap<String, String>,
+    /// User-defined properties (`meta:user-defined`), keyed by name
+    pub custom_properties: BTreeMap<String, CustomPropertyValue>,
 }
 
 /// Document statistics from metadata
@@ -108,6 +113,11 @@ impl OdfMetadata {
                         b"meta:generator" => {
                             metadata.generator = Self::extract_text_content(&mut reader, &mut buf)?;
                         },
+                        b"meta:editing-cycles" => {
+                            metadata.editing_cycles =
+                                Self::extract_text_content(&mut reader, &mut buf)?
+                                    .and_then(|cycles| cycles.parse().ok());
+                        },
                         b"meta:document-statistic" => {
                             metadata.statistics = Self::parse_document_statistics(e)?;
                         },
@@ -131,10 +141,12 @@ impl OdfMetadata {
                 },
                 Ok(Event::Eof) => break,
                 Err(e) => {
-                    return Err(Error::InvalidFormat(format!(
-                        "XML parsing error in metadata: {}",
-                        e
-                    )));
+                    return Err(Error::xml_at(
+                        Some("meta.xml"),
+                        xml_content.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
                 },
                 _ => {},
             }
@@ -162,6 +174,20 @@ impl OdfMetadata {
                         content.push_str(&String::from_utf8(t.to_vec()).unwrap_or_default());
                     }
                 },
+                Ok(Event::GeneralRef(ref r)) => {
+                    if depth == 0 {
+                        match r.resolve_char_ref() {
+                            Ok(Some(ch)) => content.push(ch),
+                            _ => {
+                                if let Some(resolved) = r.decode().ok().and_then(|name| {
+                                    quick_xml::escape::resolve_predefined_entity(&name)
+                                }) {
+                                    content.push_str(resolved);
+                                }
+                            },
+                        }
+                    }
+                },
                 Ok(Event::End(_)) => {
                     if depth == 0 {
                         break;
@@ -211,35 +237,54 @@ impl OdfMetadata {
     }
 
     /// Parse user-defined property
+    ///
+    /// The value is typed by its `meta:value-type`; values that do not parse
+    /// as their declared type are kept as strings.
     fn parse_user_defined_property(
         e: &quick_xml::events::BytesStart,
         reader: &mut quick_xml::Reader<&[u8]>,
         buf: &mut Vec<u8>,
-    ) -> Result<Option<(String, String)>> {
+    ) -> Result<Option<(String, CustomPropertyValue)>> {
         let mut name = None;
+        let mut value_type = None;
 
-        // Get property name from attributes
+        // Get property name and value type from attributes
         for attr_result in e.attributes() {
             let attr = attr_result.map_err(|_| {
                 Error::InvalidFormat("Invalid attribute in user-defined property".to_string())
             })?;
-            if attr.key.as_ref() == b"meta:name" {
-                name = Some(String::from_utf8(attr.value.to_vec()).map_err(|_| {
-                    Error::InvalidFormat("Invalid UTF-8 in property name".to_string())
-                })?);
-                break;
+            let value = String::from_utf8(attr.value.to_vec()).map_err(|_| {
+                Error::InvalidFormat("Invalid UTF-8 in user-defined property".to_string())
+            })?;
+            match attr.key.as_ref() {
+                b"meta:name" => name = Some(value),
+                b"meta:value-type" => value_type = Some(value),
+                _ => {},
             }
         }
 
-        if let Some(name) = name {
-            if let Some(value) = Self::extract_text_content(reader, buf)? {
-                Ok(Some((name, value)))
-            } else {
-                Ok(None)
-            }
-        } else {
-            Ok(None)
-        }
+        let Some(name) = name else {
+            return Ok(None);
+        };
+        let text = Self::extract_text_content(reader, buf)?.unwrap_or_default();
+
+        let value = match value_type.as_deref() {
+            Some("float" | "percentage" | "currency") => text
+                .parse()
+                .map(CustomPropertyValue::Float)
+                .unwrap_or(CustomPropertyValue::String(text)),
+            Some("boolean") => match text.as_str() {
+                "true" => CustomPropertyValue::Bool(true),
+                "false" => CustomPropertyValue::Bool(false),
+                _ => CustomPropertyValue::String(text),
+            },
+            Some("date") => Self::parse_date(Some(text.clone()))
+                .map(CustomPropertyValue::DateTime)
+                .unwrap_or(CustomPropertyValue::String(text)),
+            _ => CustomPropertyValue::String(text),
+        };
+
+        Ok(Some((name, value)))
     }
 }
 
@@ -255,11 +300,15 @@ impl OdfMetadata {
                 Some(dt.into())
             } else if let Ok(dt) = DateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%SZ") {
                 Some(dt.into())
+            } else if let Ok(dt) = NaiveDateTime::parse_from_str(&s, "%Y-%m-%dT%H:%M:%S%.f") {
+                // LibreOffice writes local times without an offset
+                Some(dt.and_utc())
             } else {
                 // Try simpler date format
-                DateTime::parse_from_str(&s, "%Y-%m-%d")
+                NaiveDate::parse_from_str(&s, "%Y-%m-%d")
                     .ok()
-                    .map(|dt| dt.into())
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|dt| dt.and_utc())
             }
         })
     }
@@ -277,17 +326,86 @@ impl From<OdfMetadata> for Metadata {
                 Some(odf_meta.keywords.join(", "))
             },
             description: odf_meta.description,
+            language: odf_meta.language,
+            revision: odf_meta.editing_cycles.map(|cycles| cycles.to_string()),
             created: OdfMetadata::parse_date(odf_meta.creation_date),
             modified: OdfMetadata::parse_date(odf_meta.modification_date),
             page_count: odf_meta.statistics.page_count,
             word_count: odf_meta.statistics.word_count,
             character_count: odf_meta.statistics.character_count,
             application: odf_meta.generator,
+            custom_properties: odf_meta.custom_properties,
             ..Default::default()
         }
     }
 }
 
+/// Serialize metadata as a complete meta.xml part.
+///
+/// `dc:date` is the time of writing and the creation date falls back to it.
+/// A numeric `revision` is written as `meta:editing-cycles`, and each custom
+/// property as a `meta:user-defined` element carrying its value type.
+pub(crate) fn meta_xml(metadata: &Metadata) -> String {
+    let now = Utc::now();
+    let created = metadata.created.unwrap_or(now);
+
+    let mut xml = String::with_capacity(512);
+    xml.push_str(
+        r#"<?xml version="1.0" encoding="UTF-8"?><office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" office:version="1.3"><office:meta><meta:generator>Litchi/0.0.1</meta:generator>"#,
+    );
+
+    let fields = [
+        ("dc:title", &metadata.title),
+        ("dc:description", &metadata.description),
+        ("dc:subject", &metadata.subject),
+        ("meta:keyword", &metadata.keywords),
+        ("dc:creator", &metadata.author),
+        ("dc:language", &metadata.language),
+    ];
+    for (element, value) in fields {
+        if let Some(value) = value {
+            let _ = write!(xml, "<{element}>{}</{element}>", escape_xml(value));
+        }
+    }
+
+    let _ = write!(
+        xml,
+        "<meta:creation-date>{}</meta:creation-date><dc:date>{}</dc:date>",
+        created.to_rfc3339_opts(SecondsFormat::Secs, true),
+        now.to_rfc3339_opts(SecondsFormat::Secs, true)
+    );
+
+    if let Some(cycles) = metadata
+        .revision
+        .as_deref()
+        .and_then(|revision| revision.trim().parse::<u32>().ok())
+    {
+        let _ = write!(xml, "<meta:editing-cycles>{cycles}</meta:editing-cycles>");
+    }
+
+    for (name, value) in &metadata.custom_properties {
+        let (value_type, text) = match value {
+            CustomPropertyValue::Bool(b) => ("boolean", b.to_string()),
+            CustomPropertyValue::Integer(i) => ("float", i.to_string()),
+            CustomPropertyValue::Float(f) => ("float", f.to_string()),
+            CustomPropertyValue::DateTime(dt) => {
+                ("date", dt.to_rfc3339_opts(SecondsFormat::Secs, true))
+            },
+            CustomPropertyValue::String(s) => ("string", s.clone()),
+        };
+        let _ = write!(
+            xml,
+            r#"<meta:user-defined meta:name="{}" meta:value-type="{}">{}</meta:user-defined>"#,
+            escape_xml(name),
+            value_type,
+            escape_xml(&text)
+        );
+    }
+
+    xml.push_str("</office:meta></office:document-meta>");
+    xml
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -478,11 +596,11 @@ mod tests {
         let meta = OdfMetadata::from_xml(xml).unwrap();
         assert_eq!(
             meta.custom_properties.get("Department"),
-            Some(&"Engineering".to_string())
+            Some(&CustomPropertyValue::String("Engineering".to_string()))
         );
         assert_eq!(
             meta.custom_properties.get("Project"),
-            Some(&"Alpha".to_string())
+            Some(&CustomPropertyValue::String("Alpha".to_string()))
         );
     }
 
@@ -521,6 +639,104 @@ mod tests {
         assert_eq!(meta.statistics.page_count, Some(10));
     }
 
+    #[test]
+    fn test_odf_metadata_from_xml_typed_user_defined() {
+        let xml = r#"<?xml version="1.0"?>
+<office:document-meta xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0">
+    <office:meta>
+        <meta:editing-cycles>7</meta:editing-cycles>
+        <meta:user-defined meta:name="Pages" meta:value-type="float">12.5</meta:user-defined>
+        <meta:user-defined meta:name="Approved" meta:value-type="boolean">true</meta:user-defined>
+        <meta:user-defined meta:name="Due" meta:value-type="date">2024-05-01</meta:user-defined>
+        <meta:user-defined meta:name="Broken" meta:value-type="float">n/a</meta:user-defined>
+    </office:meta>
+</office:document-meta>"#;
+
+        let meta = OdfMetadata::from_xml(xml).unwrap();
+        assert_eq!(meta.editing_cycles, Some(7));
+        assert_eq!(
+            meta.custom_properties.get("Pages"),
+            Some(&CustomPropertyValue::Float(12.5))
+        );
+        assert_eq!(
+            meta.custom_properties.get("Approved"),
+            Some(&CustomPropertyValue::Bool(true))
+        );
+        assert_eq!(
+            meta.custom_properties.get("Due"),
+            Some(&CustomPropertyValue::DateTime(
+                "2024-05-01T00:00:00Z".parse().unwrap()
+            ))
+        );
+        assert_eq!(
+            meta.custom_properties.get("Broken"),
+            Some(&CustomPropertyValue::String("n/a".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_meta_xml_round_trip() {
+        let created: DateTime<Utc> = "2023-11-02T08:15:00Z".parse().unwrap();
+        let due: DateTime<Utc> = "2024-05-01T12:30:00Z".parse().unwrap();
+        let mut metadata = Metadata {
+            title: Some("Quarterly <Report>".to_string()),
+            author: Some("Jane Roe".to_string()),
+            subject: Some("Finance".to_string()),
+            description: Some("Numbers & notes".to_string()),
+            keywords: Some("budget, q3".to_string()),
+            language: Some("en-GB".to_string()),
+            revision: Some("4".to_string()),
+            created: Some(created),
+            ..Default::default()
+        };
+        let properties = [
+            ("DocumentId", CustomPropertyValue::from("DOC-0042")),
+            ("Amount", CustomPropertyValue::from(1234.5)),
+            ("Due", CustomPropertyValue::from(due)),
+            ("Approved", CustomPropertyValue::from(false)),
+        ];
+        for (name, value) in properties.clone() {
+            metadata.custom_properties.insert(name.to_string(), value);
+        }
+
+        let xml = meta_xml(&metadata);
+        assert!(xml.contains(r#"meta:name="Amount" meta:value-type="float""#));
+        assert!(xml.contains(r#"meta:name="Approved" meta:value-type="boolean""#));
+        assert!(xml.contains(r#"meta:name="Due" meta:value-type="date""#));
+        assert!(xml.contains(r#"meta:name="DocumentId" meta:value-type="string""#));
+
+        let read: Metadata = OdfMetadata::from_xml(&xml).unwrap().into();
+        assert_eq!(read.title, metadata.title);
+        assert_eq!(read.author, metadata.author);
+        assert_eq!(read.subject, metadata.subject);
+        assert_eq!(read.description, metadata.description);
+        assert_eq!(read.keywords, metadata.keywords);
+        assert_eq!(read.language, metadata.language);
+        assert_eq!(read.revision, Some("4".to_string()));
+        assert_eq!(read.created, Some(created));
+        assert_eq!(read.application, Some("Litchi/0.0.1".to_string()));
+        for (name, value) in properties {
+            assert_eq!(read.custom_properties.get(name), Some(&value), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_meta_xml_integer_written_as_float() {
+        let mut metadata = Metadata::default();
+        metadata
+            .custom_properties
+            .insert("Count".to_string(), CustomPropertyValue::Integer(3));
+
+        let read = OdfMetadata::from_xml(&meta_xml(&metadata)).unwrap();
+        assert_eq!(
+            read.custom_properties.get("Count"),
+            Some(&CustomPropertyValue::Float(3.0))
+        );
+        // A non-numeric revision has no editing-cycles equivalent
+        metadata.revision = Some("draft".to_string());
+        assert!(!meta_xml(&metadata).contains("meta:editing-cycles"));
+    }
+
     #[test]
     fn test_document_statistics_default() {
         let stats = DocumentStatistics::default();
@@ -18,9 +18,15 @@
 //! ## ✅ Manifest (`manifest.rs`) - COMPLETE
 //! - ✅ `Manifest` parsing from META-INF/manifest.xml
 //! - ✅ File entry tracking with media types
-//! - ✅ Encryption info parsing (basic)
+//! - ✅ Encryption data parsing (`manifest:encryption-data`)
 //! - ✅ Manifest validation
 //!
+//! ## ✅ Decryption (`crypto.rs`, `odf_encryption` feature)
+//! - ✅ PBKDF2 keys from SHA-1 or SHA-256 start keys
+//! - ✅ Blowfish CFB and AES-CBC entries
+//! - ✅ SHA-1 and SHA-256 checksums to tell a wrong password from corruption
+//! - ❌ Argon2id keys and AES-GCM entries (LibreOffice 24.2+)
+//!
 //! ## ✅ XML Processing (`xml.rs`) - COMPLETE
 //! - ✅ `Content` - Parse content.xml (main document content)
 //! - ✅ `Styles` - Parse styles.xml (document-wide styles)
@@ -45,15 +51,20 @@
 //! - ✅ Dublin Core fields (title, creator, description, etc.)
 //! - ✅ ODF-specific fields (editing cycles, generator, etc.)
 //! - ✅ Creation and modification timestamps
+//! - ✅ Typed user-defined fields (`meta:user-defined`)
+//! - ✅ meta.xml serialization shared by the ODF writers
 //!
 //! # References
 //! - ODF Specification: §2 (Documents), §3 (Metadata)
 //! - ODF Toolkit: ODFDOM package classes
 //! - ZIP format: PKZIP Application Note
 
+/// ODF package entry decryption
+#[cfg(feature = "odf_encryption")]
+mod crypto;
 /// ODF manifest parsing
 mod manifest;
-/// ODF metadata parsing
+/// ODF metadata parsing and serialization
 mod metadata;
 /// ODF package handling
 mod package;
@@ -66,6 +77,7 @@ mod xml;
 // Manifest is internal to the package system
 #[allow(unused_imports)]
 pub use manifest::Manifest;
+pub(crate) use metadata::meta_xml;
 pub use package::OwnedPackage;
 pub use writer::{OdfStructure, PackageWriter};
-pub use xml::{Content, Meta, Styles};
+pub use xml::{Content, Meta, Settings, Styles};
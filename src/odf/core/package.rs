@@ -5,10 +5,14 @@
 //!
 //! Uses soapberry-zip for high-performance zero-copy ZIP parsing.
 
-use crate::common::{Error, Result};
+use super::manifest::{EncryptionData, ManifestEntry};
+use crate::common::{Error, Result, trace};
 use soapberry_zip::office::ArchiveReader;
 use std::io::Read;
 
+/// Entry holding the inner package of a package encrypted as a whole
+const ENCRYPTED_PACKAGE: &str = "encrypted-package";
+
 /// An ODF package (ZIP file containing XML documents)
 ///
 /// Uses soapberry-zip for efficient lazy decompression.
@@ -17,11 +21,16 @@ pub struct Package<'data> {
     #[allow(dead_code)]
     manifest: super::manifest::Manifest,
     mimetype: String,
+    /// Password that decrypts encrypted entries
+    #[cfg(feature = "odf_encryption")]
+    password: Option<&'data str>,
 }
 
 /// Owned version of Package that owns the data buffer.
 pub struct OwnedPackage {
     data: Vec<u8>,
+    #[cfg(feature = "odf_encryption")]
+    password: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -31,25 +40,39 @@ impl OwnedPackage {
         let mut data = Vec::new();
         reader.read_to_end(&mut data)?;
 
-        // Validate the archive can be parsed
-        let _ = ArchiveReader::new(&data)
-            .map_err(|_| Error::InvalidFormat("Invalid ZIP archive".to_string()))?;
-
-        Ok(Self { data })
+        Self::from_bytes(data)
     }
 
     /// Create an ODF package from bytes
     pub fn from_bytes(data: Vec<u8>) -> Result<Self> {
         // Validate the archive can be parsed
-        let _ = ArchiveReader::new(&data)
-            .map_err(|_| Error::InvalidFormat("Invalid ZIP archive".to_string()))?;
+        let _ = ArchiveReader::new(&data).map_err(Error::zip)?;
 
-        Ok(Self { data })
+        Ok(Self {
+            data,
+            #[cfg(feature = "odf_encryption")]
+            password: None,
+        })
+    }
+
+    /// Create an ODF package from bytes, decrypting encrypted entries with
+    /// `password` when they are read.
+    #[cfg(feature = "odf_encryption")]
+    pub fn from_bytes_with_password(data: Vec<u8>, password: &str) -> Result<Self> {
+        let mut package = Self::from_bytes(data)?;
+        package.password = Some(password.to_string());
+        Ok(package)
     }
 
     /// Get a borrowed Package for accessing archive contents
     pub fn package(&self) -> Result<Package<'_>> {
-        Package::new(&self.data)
+        let package = Package::new(&self.data)?;
+        #[cfg(feature = "odf_encryption")]
+        let package = Package {
+            password: self.password.as_deref(),
+            ..package
+        };
+        Ok(package)
     }
 
     /// Get the underlying data
@@ -82,6 +105,15 @@ impl OwnedPackage {
         Ok(package.has_file(path))
     }
 
+    /// Get the parsed settings.xml part, if the package has one
+    pub fn settings(&self) -> Result<Option<super::Settings>> {
+        let package = self.package()?;
+        if !package.has_file("settings.xml") {
+            return Ok(None);
+        }
+        super::Settings::from_bytes(&package.get_file("settings.xml")?).map(Some)
+    }
+
     /// List all files in the package
     pub fn files(&self) -> Result<Vec<String>> {
         let package = self.package()?;
@@ -98,13 +130,16 @@ impl OwnedPackage {
 impl<'data> Package<'data> {
     /// Create a new Package from a byte slice
     pub fn new(data: &'data [u8]) -> Result<Self> {
-        let archive = ArchiveReader::new(data)
-            .map_err(|_| Error::InvalidFormat("Invalid ZIP archive".to_string()))?;
+        let _span = trace::info_span!("open_package", format = "odf", size = data.len()).entered();
+        let archive = ArchiveReader::new(data).map_err(Error::zip)?;
 
         // Read MIME type from mimetype file
+        if !archive.contains("mimetype") {
+            return Err(Error::MissingPart("mimetype".to_string()));
+        }
         let mimetype = archive
             .read_string("mimetype")
-            .map_err(|_| Error::InvalidFormat("No mimetype file found in ODF package".to_string()))?
+            .map_err(Error::zip)?
             .trim()
             .to_string();
 
@@ -115,6 +150,8 @@ impl<'data> Package<'data> {
             archive,
             manifest,
             mimetype,
+            #[cfg(feature = "odf_encryption")]
+            password: None,
         })
     }
 
@@ -124,10 +161,59 @@ impl<'data> Package<'data> {
     }
 
     /// Get a file from the package by path
+    ///
+    /// Entries that the manifest marks as encrypted are decrypted with the
+    /// password of the package.
     pub fn get_file(&self, path: &str) -> Result<Vec<u8>> {
-        self.archive
-            .read(path)
-            .map_err(|_| Error::InvalidFormat(format!("File not found: {}", path)))
+        if !self.archive.contains(path) {
+            // ODF 1.4 packages may hold a single encrypted inner package
+            if self.archive.contains(ENCRYPTED_PACKAGE) {
+                return Err(Error::UnsupportedEncryption(
+                    "whole-package encryption (encrypted-package)".to_string(),
+                ));
+            }
+            return Err(Error::MissingPart(path.to_string()));
+        }
+        let data = self.archive.read(path).map_err(Error::zip)?;
+
+        let entry = self
+            .manifest
+            .get_entry(path.strip_prefix('/').unwrap_or(path));
+        match entry.and_then(|entry| Some((entry, entry.encryption.as_ref()?))) {
+            Some((entry, encryption)) => self.decrypt(entry, encryption, &data),
+            None => Ok(data),
+        }
+    }
+
+    /// Decrypt an entry that the manifest marks as encrypted
+    #[cfg(feature = "odf_encryption")]
+    fn decrypt(
+        &self,
+        entry: &ManifestEntry,
+        encryption: &EncryptionData,
+        data: &[u8],
+    ) -> Result<Vec<u8>> {
+        match self.password {
+            Some(password) => super::crypto::decrypt_entry(entry, encryption, data, password),
+            None => Err(Error::Encrypted(format!(
+                "{} is password-protected, open the document with a password",
+                entry.full_path
+            ))),
+        }
+    }
+
+    /// Decrypt an entry that the manifest marks as encrypted
+    #[cfg(not(feature = "odf_encryption"))]
+    fn decrypt(
+        &self,
+        entry: &ManifestEntry,
+        _encryption: &EncryptionData,
+        _data: &[u8],
+    ) -> Result<Vec<u8>> {
+        Err(Error::Encrypted(format!(
+            "{} is password-protected, decrypting it requires the `odf_encryption` feature",
+            entry.full_path
+        )))
     }
 
     /// Check if a file exists in the package
@@ -499,4 +585,79 @@ mod tests {
         assert!(media_files.contains(&"Object/image.svg".to_string()));
         assert!(media_files.contains(&"media/diagram.png".to_string()));
     }
+
+    #[cfg(feature = "odf_encryption")]
+    fn encrypted_fixture(name: &str) -> Vec<u8> {
+        let path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/odf")
+            .join(name);
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    #[cfg(feature = "odf_encryption")]
+    fn test_encrypted_entries_are_decrypted() {
+        for name in ["encrypted_aes.odt", "encrypted_blowfish.odt"] {
+            let package =
+                OwnedPackage::from_bytes_with_password(encrypted_fixture(name), "secret").unwrap();
+
+            let content = String::from_utf8(package.get_file("content.xml").unwrap()).unwrap();
+            assert!(content.starts_with("<?xml"), "{}", name);
+            assert!(content.contains("Protected report"), "{}", name);
+
+            // Images are encrypted without being deflated first
+            let media = package.media_files().unwrap();
+            let image = package.get_file(&media[0]).unwrap();
+            assert!(image.starts_with(b"\x89PNG"), "{}", name);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "odf_encryption")]
+    fn test_encrypted_entry_wrong_password() {
+        for name in ["encrypted_aes.odt", "encrypted_blowfish.ods"] {
+            let package =
+                OwnedPackage::from_bytes_with_password(encrypted_fixture(name), "guess").unwrap();
+            let result = package.get_file("content.xml");
+            assert!(
+                matches!(result, Err(Error::IncorrectPassword(_))),
+                "{}: {:?}",
+                name,
+                result.err()
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "odf_encryption")]
+    fn test_encrypted_entry_without_password() {
+        let package = OwnedPackage::from_bytes(encrypted_fixture("encrypted_aes.ods")).unwrap();
+        assert!(matches!(
+            package.get_file("content.xml"),
+            Err(Error::Encrypted(_))
+        ));
+        // The manifest is never encrypted
+        assert!(package.get_file("META-INF/manifest.xml").is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "odf_encryption")]
+    fn test_open_encrypted_documents() {
+        use crate::odf::{Document, Spreadsheet};
+
+        let doc =
+            Document::from_bytes_with_password(encrypted_fixture("encrypted_aes.odt"), "secret")
+                .unwrap();
+        assert!(doc.text().unwrap().contains("not in this aes document"));
+
+        for name in ["encrypted_aes.ods", "encrypted_blowfish.ods"] {
+            let mut spreadsheet =
+                Spreadsheet::from_bytes_with_password(encrypted_fixture(name), "secret").unwrap();
+            let sheets = spreadsheet.sheets().unwrap();
+            assert_eq!(sheets[0].name().unwrap(), "Budget", "{}", name);
+        }
+
+        let result = Document::from_bytes(encrypted_fixture("encrypted_blowfish.odt"));
+        assert!(matches!(result, Err(Error::Encrypted(_))));
+    }
 }
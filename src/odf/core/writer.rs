@@ -79,7 +79,7 @@ impl PackageWriter {
 
         self.zip_writer
             .write_stored("mimetype", mimetype.as_bytes())
-            .map_err(|e| Error::ZipError(e.to_string()))?;
+            .map_err(Error::zip)?;
         self.wrote_any_entry = true;
         self.wrote_mimetype = true;
 
@@ -122,7 +122,7 @@ impl PackageWriter {
 
         self.zip_writer
             .write_deflated(path, content)
-            .map_err(|e| Error::ZipError(e.to_string()))?;
+            .map_err(Error::zip)?;
 
         self.wrote_any_entry = true;
 
@@ -160,7 +160,7 @@ impl PackageWriter {
 
         self.zip_writer
             .write_deflated(path, content)
-            .map_err(|e| Error::ZipError(e.to_string()))?;
+            .map_err(Error::zip)?;
 
         self.wrote_any_entry = true;
 
@@ -238,12 +238,10 @@ impl PackageWriter {
         let manifest_content = self.generate_manifest();
         self.zip_writer
             .write_deflated("META-INF/manifest.xml", manifest_content.as_bytes())
-            .map_err(|e| Error::ZipError(e.to_string()))?;
+            .map_err(Error::zip)?;
 
         // Finish ZIP archive and return bytes
-        self.zip_writer
-            .finish_to_bytes()
-            .map_err(|e| Error::ZipError(e.to_string()))
+        self.zip_writer.finish_to_bytes().map_err(Error::zip)
     }
 
     /// Alias for `finish()` for API compatibility.
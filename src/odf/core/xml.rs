@@ -173,10 +173,139 @@ impl Meta {
     }
 }
 
+/// Parsed settings.xml part
+#[derive(Debug)]
+pub struct Settings {
+    xml: XmlPart,
+}
+
+impl Settings {
+    /// Parse settings from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let xml = XmlPart::from_bytes(bytes)?;
+        Ok(Self { xml })
+    }
+
+    /// Get the raw XML content
+    #[allow(dead_code)]
+    pub fn xml_content(&self) -> &str {
+        self.xml.content()
+    }
+
+    /// Get the value of the first `config:config-item` with the given name.
+    ///
+    /// Returns `None` if there is no such item. Items that are maps or
+    /// sequences have no value.
+    pub fn config_item(&self, name: &str) -> Option<String> {
+        use quick_xml::Reader;
+        use quick_xml::events::Event;
+
+        let named = |e: &quick_xml::events::BytesStart| {
+            e.name().as_ref() == b"config:config-item"
+                && e.attributes().flatten().any(|attr| {
+                    attr.key.as_ref() == b"config:name" && attr.value.as_ref() == name.as_bytes()
+                })
+        };
+        let mut reader = Reader::from_str(self.xml.content());
+        let mut in_item = false;
+        let mut value = String::new();
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => in_item = named(&e),
+                Ok(Event::Empty(e)) if named(&e) => return Some(String::new()),
+                Ok(Event::Text(t)) if in_item => {
+                    value.push_str(&String::from_utf8_lossy(t.as_ref()));
+                },
+                Ok(Event::End(e)) if in_item && e.name().as_ref() == b"config:config-item" => {
+                    return Some(value);
+                },
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => {},
+            }
+        }
+    }
+
+    /// Check whether any config element, item or map, has the given name.
+    pub fn has_config(&self, name: &str) -> bool {
+        self.xml
+            .content()
+            .contains(&format!(r#"config:name="{}""#, name))
+    }
+
+    /// Get the editing protection declared by the settings.
+    ///
+    /// `LoadReadonly` recommends opening the file read-only, and
+    /// `ModifyPasswordInfo` holds a password to modify. A
+    /// `RedlineProtectionKey` with `RecordChanges` protects the recording of
+    /// tracked changes with a password.
+    pub fn protection(&self) -> crate::common::Protection {
+        use crate::common::{Protection, ProtectionKind};
+
+        let is_true = |name| self.config_item(name).is_some_and(|value| value == "true");
+        let mut protection = Protection::default();
+        if is_true("LoadReadonly") {
+            protection.kind = ProtectionKind::ReadOnlyRecommended;
+        }
+        if is_true("RecordChanges")
+            && self
+                .config_item("RedlineProtectionKey")
+                .is_some_and(|key| !key.is_empty())
+        {
+            protection = protection.or(Protection::with_password(ProtectionKind::TrackedChanges));
+        }
+        if self.has_config("ModifyPasswordInfo") {
+            protection = protection.or(Protection::with_password(ProtectionKind::ReadOnly));
+        }
+        protection
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_settings_protection() {
+        use crate::common::{Protection, ProtectionKind};
+
+        let xml = br#"<office:document-settings xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:config="urn:oasis:names:tc:opendocument:xmlns:config:1.0">
+            <office:settings>
+                <config:config-item-set config:name="ooo:configuration-settings">
+                    <config:config-item config:name="LoadReadonly" config:type="boolean">true</config:config-item>
+                    <config:config-item config:name="RecordChanges" config:type="boolean">false</config:config-item>
+                    <config:config-item config:name="RedlineProtectionKey" config:type="base64Binary"/>
+                </config:config-item-set>
+            </office:settings>
+        </office:document-settings>"#;
+        let settings = Settings::from_bytes(xml).unwrap();
+        assert_eq!(
+            settings.config_item("LoadReadonly").as_deref(),
+            Some("true")
+        );
+        assert_eq!(
+            settings.config_item("RedlineProtectionKey").as_deref(),
+            Some("")
+        );
+        assert_eq!(settings.config_item("Missing"), None);
+        assert_eq!(
+            settings.protection(),
+            Protection::new(ProtectionKind::ReadOnlyRecommended)
+        );
+
+        let xml = String::from_utf8(xml.to_vec())
+            .unwrap()
+            .replace(">false<", ">true<")
+            .replace(
+                r#"config:type="base64Binary"/>"#,
+                r#"config:type="base64Binary">AAAA</config:config-item>"#,
+            );
+        let settings = Settings::from_bytes(xml.as_bytes()).unwrap();
+        assert_eq!(
+            settings.protection(),
+            Protection::with_password(ProtectionKind::TrackedChanges)
+        );
+    }
+
     #[test]
     fn test_xml_part_from_bytes() {
         let xml = b"<?xml version=\"1.0\"?><root><child>text</child></root>";
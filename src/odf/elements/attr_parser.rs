@@ -21,6 +21,7 @@
 //! - ODF 1.2 Specification: Attribute types and valid values
 //! - odfpy: `3rdparty/odfpy/odf/attrconverters.py`
 //! - odfdo: `3rdparty/odfdo/src/odfdo/element.py`
+use crate::common::unit::split_length;
 use crate::common::{Error, Result};
 use atoi_simd::parse;
 use fast_float2::parse_partial;
@@ -331,17 +332,10 @@ pub fn parse_length(value: &[u8]) -> Result<(f64, Cow<'_, str>)> {
     let s = std::str::from_utf8(value)
         .map_err(|_| Error::InvalidFormat("Invalid UTF-8 in length value".to_string()))?;
 
-    // Try to parse the numeric part
-    match parse_partial::<f64, _>(s) {
-        Ok((num, consumed)) => {
-            let unit = &s[consumed..].trim();
-            if unit.is_empty() {
-                // Default unit is points
-                Ok((num, Cow::Borrowed("pt")))
-            } else {
-                Ok((num, Cow::Borrowed(unit)))
-            }
-        },
+    match split_length(s) {
+        // Default unit is points
+        Ok((num, "")) => Ok((num, Cow::Borrowed("pt"))),
+        Ok((num, unit)) => Ok((num, Cow::Borrowed(unit))),
         Err(_) => Err(Error::InvalidFormat(format!("Invalid length value: {}", s))),
     }
 }
@@ -295,7 +295,7 @@ impl Element {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(Error::InvalidFormat(format!("XML parsing error: {}", e))),
+                Err(e) => return Err(Error::xml_at(None, bytes, reader.error_position(), e)),
                 _ => {},
             }
             buf.clear();
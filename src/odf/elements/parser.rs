@@ -171,6 +171,22 @@ impl DocumentParser {
                         element.set_text(&format!("{}{}", current_text, text));
                     }
                 },
+                Ok(Event::GeneralRef(ref r)) => {
+                    // Entities and character references such as `&amp;` and `&#x20AC;`
+                    if let Some((_, element)) = element_stack.last_mut() {
+                        let resolved = match r.resolve_char_ref() {
+                            Ok(Some(ch)) => Some(ch.to_string()),
+                            _ => r.decode().ok().and_then(|name| {
+                                quick_xml::escape::resolve_predefined_entity(&name)
+                                    .map(str::to_string)
+                            }),
+                        };
+                        if let Some(resolved) = resolved {
+                            let current_text = element.text().to_string();
+                            element.set_text(&format!("{}{}", current_text, resolved));
+                        }
+                    }
+                },
                 Ok(Event::End(ref e)) => {
                     let tag_name = String::from_utf8_lossy(e.name().as_ref()).to_string();
 
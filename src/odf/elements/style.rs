@@ -4,7 +4,9 @@
 //! including parsing, inheritance, and property resolution.
 
 use super::element::{Element, ElementBase};
-use crate::common::Result;
+use crate::common::xml::qualified_attr;
+use crate::common::{Language, Result};
+use quick_xml::events::{BytesStart, Event};
 use std::borrow::Cow;
 use std::collections::HashMap;
 
@@ -546,6 +548,197 @@ impl StyleRegistry {
     }
 }
 
+/// The resolved languages of the paragraph and text styles of a document.
+///
+/// Attached to paragraphs by the document so that
+/// [`Span::language`](super::text::Span::language) can take styles into
+/// account.
+#[derive(Debug, Default)]
+pub(crate) struct StyleLanguages {
+    /// Languages of the default paragraph style
+    defaults: Language,
+    /// Paragraph styles by name, including the defaults
+    paragraph: HashMap<String, Language>,
+    /// Text styles by name, without the defaults
+    text: HashMap<String, Language>,
+}
+
+/// A style read for its languages.
+struct LanguageStyle {
+    parent: Option<String>,
+    language: Language,
+}
+
+impl StyleLanguages {
+    /// Read the language declarations (`fo:language`, `style:language-asian`,
+    /// `style:language-complex` and their RFC 3066 variants) of the styles
+    /// in `parts`, usually `styles.xml` then `content.xml`.
+    pub fn from_xml(parts: &[&str]) -> Self {
+        let mut default = Language::default();
+        let mut paragraph: HashMap<String, LanguageStyle> = HashMap::new();
+        let mut text: HashMap<String, LanguageStyle> = HashMap::new();
+
+        for xml in parts {
+            let mut reader = quick_xml::Reader::from_str(xml);
+            // Family and name of the style being read; no name is the default style
+            let mut current: Option<(bool, Option<String>)> = None;
+            loop {
+                let (e, empty) = match reader.read_event() {
+                    Ok(Event::Start(e)) => (e, false),
+                    Ok(Event::Empty(e)) => (e, true),
+                    Ok(Event::End(e)) => {
+                        if matches!(e.name().as_ref(), b"style:style" | b"style:default-style") {
+                            current = None;
+                        }
+                        continue;
+                    },
+                    Ok(Event::Eof) | Err(_) => break,
+                    _ => continue,
+                };
+                match e.name().as_ref() {
+                    // Styles never follow the body
+                    b"office:body" => break,
+                    name @ (b"style:style" | b"style:default-style") => {
+                        let family = qualified_attr(&e, b"style:family");
+                        let is_paragraph = match family.as_deref() {
+                            Some("paragraph") => true,
+                            Some("text") => false,
+                            _ => continue,
+                        };
+                        let style_name = if name == b"style:style" {
+                            let Some(style_name) = qualified_attr(&e, b"style:name") else {
+                                continue;
+                            };
+                            let map = if is_paragraph {
+                                &mut paragraph
+                            } else {
+                                &mut text
+                            };
+                            map.insert(
+                                style_name.clone(),
+                                LanguageStyle {
+                                    parent: qualified_attr(&e, b"style:parent-style-name"),
+                                    language: Language::default(),
+                                },
+                            );
+                            Some(style_name)
+                        } else if is_paragraph {
+                            None
+                        } else {
+                            continue;
+                        };
+                        if !empty {
+                            current = Some((is_paragraph, style_name));
+                        }
+                    },
+                    b"style:text-properties" => {
+                        let language = text_properties_language(&e);
+                        match &current {
+                            Some((_, None)) => default.apply(&language),
+                            Some((is_paragraph, Some(name))) => {
+                                let map = if *is_paragraph {
+                                    &mut paragraph
+                                } else {
+                                    &mut text
+                                };
+                                if let Some(style) = map.get_mut(name) {
+                                    style.language.apply(&language);
+                                }
+                            },
+                            None => {},
+                        }
+                    },
+                    _ => {},
+                }
+            }
+        }
+
+        let resolve = |styles: &HashMap<String, LanguageStyle>, base: &Language| {
+            styles
+                .iter()
+                .map(|(name, style)| {
+                    // Apply the parent chain from its root down, guarding
+                    // against cycles in malformed chains
+                    let mut chain = vec![style];
+                    while chain.len() < styles.len().min(64)
+                        && let Some(parent) = chain[chain.len() - 1]
+                            .parent
+                            .as_deref()
+                            .and_then(|parent| styles.get(parent))
+                    {
+                        chain.push(parent);
+                    }
+                    let mut language = base.clone();
+                    for style in chain.iter().rev() {
+                        language.apply(&style.language);
+                    }
+                    (name.clone(), language)
+                })
+                .collect()
+        };
+
+        Self {
+            paragraph: resolve(&paragraph, &default),
+            text: resolve(&text, &Language::default()),
+            defaults: default,
+        }
+    }
+
+    /// Get the languages of the default paragraph style.
+    pub fn defaults(&self) -> &Language {
+        &self.defaults
+    }
+
+    /// Check whether no style declares a language.
+    pub fn is_empty(&self) -> bool {
+        self.defaults.is_empty()
+            && self.paragraph.values().all(Language::is_empty)
+            && self.text.values().all(Language::is_empty)
+    }
+
+    /// Get the languages of a paragraph style. A missing or unknown style
+    /// stands for the default paragraph style.
+    pub fn paragraph(&self, name: Option<&str>) -> &Language {
+        name.and_then(|name| self.paragraph.get(name))
+            .unwrap_or(&self.defaults)
+    }
+
+    /// Get the languages of a text style.
+    pub fn text(&self, name: &str) -> Option<&Language> {
+        self.text.get(name)
+    }
+}
+
+/// Get the languages declared by a `style:text-properties` element.
+fn text_properties_language(e: &BytesStart<'_>) -> Language {
+    let tag = |rfc: &[u8], language: &[u8], country: &[u8]| {
+        if let Some(tag) = qualified_attr(e, rfc) {
+            return Some(tag);
+        }
+        // "none" marks text without a language or country
+        let language = qualified_attr(e, language).filter(|language| language != "none")?;
+        Some(
+            match qualified_attr(e, country).filter(|country| country != "none") {
+                Some(country) => format!("{language}-{country}"),
+                None => language,
+            },
+        )
+    };
+    Language {
+        latin: tag(b"style:rfc-language-tag", b"fo:language", b"fo:country"),
+        east_asian: tag(
+            b"style:rfc-language-tag-asian",
+            b"style:language-asian",
+            b"style:country-asian",
+        ),
+        complex: tag(
+            b"style:rfc-language-tag-complex",
+            b"style:language-complex",
+            b"style:country-complex",
+        ),
+    }
+}
+
 /// Collection of style elements for easy parsing
 pub struct StyleElements;
 
@@ -806,4 +999,51 @@ mod tests {
         assert_eq!(element.tag_name(), "style:style");
         assert_eq!(element.get_attribute("style:name"), Some("Test"));
     }
+
+    #[test]
+    fn test_style_languages() {
+        let styles = r#"<office:styles>
+            <style:default-style style:family="paragraph">
+                <style:text-properties fo:language="en" fo:country="GB"
+                    style:language-asian="zh" style:country-asian="CN"
+                    style:language-complex="none" style:country-complex="none"/>
+            </style:default-style>
+            <style:style style:name="Standard" style:family="paragraph"/>
+            <style:style style:name="Quote" style:family="paragraph" style:parent-style-name="Standard">
+                <style:text-properties fo:language="de" fo:country="DE"/>
+            </style:style>
+        </office:styles>"#;
+        let content = r#"<office:automatic-styles>
+            <style:style style:name="P1" style:family="paragraph" style:parent-style-name="Quote"/>
+            <style:style style:name="T1" style:family="text">
+                <style:text-properties style:rfc-language-tag-asian="ja-JP"/>
+            </style:style>
+        </office:automatic-styles>
+        <office:body><style:style style:name="T2" style:family="text"/></office:body>"#;
+        let languages = StyleLanguages::from_xml(&[styles, content]);
+
+        let defaults = Language {
+            latin: Some("en-GB".to_string()),
+            east_asian: Some("zh-CN".to_string()),
+            complex: None,
+        };
+        assert_eq!(languages.defaults(), &defaults);
+        assert_eq!(languages.paragraph(None), &defaults);
+        assert_eq!(languages.paragraph(Some("Standard")), &defaults);
+        assert_eq!(languages.paragraph(Some("Unknown")), &defaults);
+        assert_eq!(
+            languages.paragraph(Some("P1")).latin.as_deref(),
+            Some("de-DE")
+        );
+        assert_eq!(
+            languages.text("T1"),
+            Some(&Language {
+                east_asian: Some("ja-JP".to_string()),
+                ..Default::default()
+            })
+        );
+        // Styles are not read past the body
+        assert_eq!(languages.text("T2"), None);
+        assert!(!languages.is_empty());
+    }
 }
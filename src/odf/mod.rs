@@ -232,13 +232,21 @@ pub use crate::common::unit::{Length, LengthUnit};
 // Re-export main types for convenience
 pub use odp::{MutablePresentation, Presentation, PresentationBuilder};
 pub use ods::{
-    Cell as SCell, CellValue, MutableSpreadsheet, Row as SRow, Sheet, Spreadsheet,
-    SpreadsheetBuilder,
+    Cell as SCell, CellComment, CellValue, MutableSpreadsheet, NamedRange, Row as SRow, Sheet,
+    SheetProtection, Spreadsheet, SpreadsheetBuilder,
 };
-pub use odt::{Document, DocumentBuilder, MutableDocument};
 
-// Re-export shapes for presentations
-pub use odp::{Shape, Slide};
+// Re-export OpenFormula translation for evaluating ODS formulas
+pub use ods::to_excel_formula;
+#[cfg(feature = "eval_engine")]
+pub use ods::to_expr;
+pub use odt::{ChangeType, Document, DocumentBuilder, MutableDocument, TrackChange};
+
+// Re-export shapes and transitions for presentations
+pub use odp::{
+    ClockDirection, Shape, Slide, SlideTransition, TransitionDirection, TransitionSpeed,
+    TransitionType, ZoomDirection,
+};
 
 // Re-export document element types for unified API (for ODT tables)
 pub use elements::table::{Table, TableCell as Cell, TableRow as Row};
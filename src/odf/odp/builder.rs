@@ -2,8 +2,8 @@
 //!
 //! This module provides a builder pattern for creating new ODP presentations from scratch.
 
-use crate::common::{Metadata, Result, xml::escape_xml};
-use crate::odf::core::{OdfStructure, PackageWriter};
+use crate::common::{CustomPropertyValue, Metadata, Result, xml::escape_xml};
+use crate::odf::core::{OdfStructure, PackageWriter, meta_xml};
 use crate::odf::odp::Slide;
 use std::path::Path;
 
@@ -62,6 +62,43 @@ impl PresentationBuilder {
         self.metadata = metadata;
     }
 
+    /// Get a mutable reference to the document metadata
+    ///
+    /// Every field written to meta.xml can be set here, including the
+    /// language and the revision, which is written as the editing cycles.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Set a user-defined metadata field
+    ///
+    /// The field is written as a `meta:user-defined` element whose value type
+    /// (string, float, date or boolean) follows the value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the field
+    /// * `value` - Value of the field
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use litchi::odf::PresentationBuilder;
+    ///
+    /// let mut builder = PresentationBuilder::new();
+    /// builder.set_user_defined("DocumentId", "DOC-0042");
+    /// builder.set_user_defined("Reviewed", true);
+    /// ```
+    pub fn set_user_defined(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<CustomPropertyValue>,
+    ) {
+        self.metadata
+            .custom_properties
+            .insert(name.into(), value.into());
+    }
+
     /// Add a slide with title and text content
     ///
     /// # Arguments
@@ -86,6 +123,7 @@ impl PresentationBuilder {
             text: text.to_string(),
             index: self.slides.len(),
             notes: None,
+            transition: None,
             shapes: Vec::new(),
         };
         self.slides.push(slide);
@@ -115,6 +153,7 @@ impl PresentationBuilder {
             text: text.to_string(),
             index: self.slides.len(),
             notes: None,
+            transition: None,
             shapes: Vec::new(),
         };
         self.slides.push(slide);
@@ -139,6 +178,7 @@ impl PresentationBuilder {
     ///     text: "Custom content".to_string(),
     ///     index: 0,
     ///     notes: Some("Speaker notes".to_string()),
+    ///     transition: None,
     ///     shapes: vec![],
     /// };
     /// builder.add_slide_element(slide)?;
@@ -151,6 +191,55 @@ impl PresentationBuilder {
         Ok(self)
     }
 
+    /// Set the speaker notes of a slide
+    ///
+    /// Each line of `notes` is written as its own paragraph on the slide's
+    /// notes page.
+    ///
+    /// # Arguments
+    ///
+    /// * `slide_index` - Index of the slide (0-based)
+    /// * `notes` - Speaker notes text
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use litchi::odf::PresentationBuilder;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let mut builder = PresentationBuilder::new();
+    /// builder.add_slide_with_title("Introduction", "Welcome")?;
+    /// builder.set_notes(0, "Greet the audience")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_notes(&mut self, slide_index: usize, notes: &str) -> Result<&mut Self> {
+        let count = self.slides.len();
+        let slide = self.slides.get_mut(slide_index).ok_or_else(|| {
+            crate::common::Error::InvalidFormat(format!(
+                "Slide index {} out of bounds (length: {})",
+                slide_index, count
+            ))
+        })?;
+        slide.notes = Some(notes.to_string());
+        Ok(self)
+    }
+
+    /// Generate the `presentation:notes` element for a slide's speaker notes
+    pub(crate) fn generate_notes_xml(notes: &str, page_number: usize) -> String {
+        let mut xml = format!(
+            r#"<presentation:notes><draw:page-thumbnail draw:layer="layout" svg:width="14.848cm" svg:height="8.35cm" svg:x="3.075cm" svg:y="2.257cm" draw:page-number="{}" presentation:class="page"/><draw:frame draw:layer="layout" svg:width="16.799cm" svg:height="13.364cm" svg:x="2.1cm" svg:y="11.512cm" presentation:class="notes"><draw:text-box>"#,
+            page_number
+        );
+        for line in notes.lines() {
+            xml.push_str("<text:p>");
+            xml.push_str(&escape_xml(line));
+            xml.push_str("</text:p>");
+        }
+        xml.push_str("</draw:text-box></draw:frame></presentation:notes>");
+        xml
+    }
+
     /// Generate XML for a shape
     fn generate_shape_xml(shape: &crate::odf::odp::Shape, idx: usize) -> String {
         use crate::common::ShapeType;
@@ -290,6 +379,10 @@ impl PresentationBuilder {
                 body.push_str(&Self::generate_shape_xml(shape, shape_idx));
             }
 
+            if let Some(ref notes) = slide.notes {
+                body.push_str(&Self::generate_notes_xml(notes, i + 1));
+            }
+
             body.push_str("</draw:page>");
         }
 
@@ -308,26 +401,7 @@ impl PresentationBuilder {
 
     /// Generate meta.xml with metadata
     fn generate_meta_xml(&self) -> String {
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let mut meta = format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" office:version="1.3"><office:meta><meta:generator>Litchi/0.0.1</meta:generator><meta:creation-date>{}</meta:creation-date><dc:date>{}</dc:date>"#,
-            now, now
-        );
-
-        // Add optional metadata fields
-        if let Some(ref title) = self.metadata.title {
-            meta.push_str(&format!("<dc:title>{}</dc:title>", escape_xml(title)));
-        }
-
-        if let Some(ref author) = self.metadata.author {
-            meta.push_str(&format!("<dc:creator>{}</dc:creator>", escape_xml(author)));
-        }
-
-        meta.push_str("</office:meta>");
-        meta.push_str("</office:document-meta>");
-
-        meta
+        meta_xml(&self.metadata)
     }
 
     /// Build the presentation and return as bytes
@@ -390,3 +464,38 @@ impl PresentationBuilder {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::odf::{MutablePresentation, Presentation};
+
+    #[test]
+    fn test_notes_read_back() {
+        let mut builder = PresentationBuilder::new();
+        builder.add_slide_with_title("Intro", "Welcome").unwrap();
+        builder.add_slide("No notes here").unwrap();
+        builder
+            .set_notes(0, "Greet the audience\nMention the agenda")
+            .unwrap();
+        assert!(builder.set_notes(2, "Out of range").is_err());
+
+        let presentation = Presentation::from_bytes(builder.build().unwrap()).unwrap();
+        let slides = presentation.slides().unwrap();
+        assert_eq!(slides.len(), 2);
+        assert_eq!(slides[0].text().unwrap(), "Intro Welcome");
+        assert_eq!(
+            slides[0].notes_text().unwrap(),
+            "Greet the audience\nMention the agenda"
+        );
+        assert_eq!(slides[1].notes().unwrap(), None);
+
+        // Notes survive a round trip through the mutable presentation
+        let mutable = MutablePresentation::from_presentation(presentation).unwrap();
+        let reloaded = Presentation::from_bytes(mutable.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            reloaded.slides().unwrap()[0].notes_text().unwrap(),
+            "Greet the audience\nMention the agenda"
+        );
+    }
+}
@@ -14,6 +14,8 @@
 //! - ✅ `Slide::shapes()` - Get shapes on a slide
 //! - ✅ `Slide::text()` - Extract text from slide
 //! - ✅ `Slide::layout()` - Get slide layout name
+//! - ✅ `Slide::notes_text()` - Extract speaker notes
+//! - ✅ `Slide::transition()` - Read slide transitions (ODF 1.0 styles and SMIL)
 //! - ✅ `Shape` parsing (text boxes, rectangles, ellipses, images)
 //! - ✅ Master page parsing
 //! - ✅ Metadata extraction
@@ -23,15 +25,15 @@
 //! - ✅ `PresentationBuilder::new()` - Create new presentations
 //! - ✅ `add_slide()` - Add slides
 //! - ✅ `add_shape()` - Add shapes (text boxes, rectangles, etc.)
+//! - ✅ `set_notes()` - Set speaker notes
 //! - ✅ `set_slide_layout()` - Set slide layout
-//! - ✅ `set_title()` / `set_author()` - Set metadata
+//! - ✅ `set_metadata()` / `metadata_mut()` / `set_user_defined()` - Set metadata
 //! - ✅ `save()` / `to_bytes()` - Write to file or bytes
 //! - ✅ `MutablePresentation` - Modify existing presentations
 //!
 //! ## 🚧 TODO - Advanced Features
-//! - ⚠️ Slide transitions (fade, wipe, push, etc.)
+//! - ⚠️ Writing slide transitions
 //! - ⚠️ Animations (entrance, emphasis, exit, motion paths)
-//! - ⚠️ Speaker notes (notes pages)
 //! - ⚠️ Multimedia embedding (audio, video)
 //! - ⚠️ Custom slide layouts
 //! - ⚠️ Advanced shape properties (gradients, shadows, 3D effects)
@@ -53,8 +55,13 @@ mod mutable;
 mod parser;
 mod presentation;
 mod slide;
+mod transition;
 
 pub use builder::PresentationBuilder;
 pub use mutable::MutablePresentation;
 pub use presentation::Presentation;
 pub use slide::{Shape, Slide};
+pub use transition::{
+    ClockDirection, SlideTransition, TransitionDirection, TransitionSpeed, TransitionType,
+    ZoomDirection,
+};
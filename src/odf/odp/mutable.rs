@@ -3,9 +3,9 @@
 //! This module provides a mutable wrapper around ODP presentations that allows
 //! for in-place modification of slides, shapes, and content.
 
-use crate::common::{Metadata, Result, xml::escape_xml};
-use crate::odf::core::{OdfStructure, PackageWriter};
-use crate::odf::odp::{Presentation, Shape, Slide};
+use crate::common::{CustomPropertyValue, Metadata, Result, xml::escape_xml};
+use crate::odf::core::{OdfStructure, PackageWriter, meta_xml};
+use crate::odf::odp::{Presentation, PresentationBuilder, Shape, Slide};
 use std::path::Path;
 
 /// A mutable ODP presentation that supports in-place modifications.
@@ -118,6 +118,20 @@ impl MutablePresentation {
         &mut self.metadata
     }
 
+    /// Set a user-defined metadata field.
+    ///
+    /// The field is written as a `meta:user-defined` element whose value type
+    /// (string, float, date or boolean) follows the value.
+    pub fn set_user_defined(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<CustomPropertyValue>,
+    ) {
+        self.metadata
+            .custom_properties
+            .insert(name.into(), value.into());
+    }
+
     /// Add a new slide to the end of the presentation.
     ///
     /// # Arguments
@@ -143,6 +157,7 @@ impl MutablePresentation {
             text: text.to_string(),
             index: self.slides.len(),
             notes: None,
+            transition: None,
             shapes: Vec::new(),
         };
         self.slides.push(slide);
@@ -177,6 +192,7 @@ impl MutablePresentation {
                 text: text.to_string(),
                 index,
                 notes: None,
+                transition: None,
                 shapes: Vec::new(),
             };
             self.slides.insert(index, slide);
@@ -471,6 +487,10 @@ impl MutablePresentation {
                 }
             }
 
+            if let Some(ref notes) = slide.notes {
+                body.push_str(&PresentationBuilder::generate_notes_xml(notes, page_num));
+            }
+
             body.push_str("</draw:page>");
         }
 
@@ -482,47 +502,7 @@ impl MutablePresentation {
 
     /// Generate meta.xml with current metadata.
     fn generate_meta_xml(&self) -> String {
-        let now = chrono::Utc::now().to_rfc3339();
-        let mut estimated = 64usize;
-        estimated += self.metadata.title.as_ref().map(|s| s.len()).unwrap_or(0);
-        estimated += self.metadata.author.as_ref().map(|s| s.len()).unwrap_or(0);
-        estimated += self.metadata.subject.as_ref().map(|s| s.len()).unwrap_or(0);
-        estimated += self
-            .metadata
-            .description
-            .as_ref()
-            .map(|s| s.len())
-            .unwrap_or(0);
-        estimated += self
-            .metadata
-            .keywords
-            .as_ref()
-            .map(|s| s.len())
-            .unwrap_or(0);
-        let mut meta_fields = String::with_capacity(estimated);
-
-        // Add optional metadata fields
-        if let Some(ref title) = self.metadata.title {
-            let escaped_title = escape_xml(title);
-            meta_fields.push_str(&xml_minifier::minified_xml_format!(
-                r#"<dc:title>{}</dc:title>"#,
-                escaped_title
-            ));
-        }
-
-        if let Some(ref author) = self.metadata.author {
-            let escaped_author = escape_xml(author);
-            meta_fields.push_str(&xml_minifier::minified_xml_format!(
-                r#"<dc:creator>{}</dc:creator>"#,
-                escaped_author
-            ));
-        }
-
-        xml_minifier::minified_xml_format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" office:version="1.3"><office:meta><meta:generator>Litchi/0.0.1</meta:generator><dc:date>{}</dc:date>{}</office:meta></office:document-meta>"#,
-            now,
-            meta_fields
-        )
+        meta_xml(&self.metadata)
     }
 
     /// Save the modified presentation to a file.
@@ -1,9 +1,10 @@
 //! ODP-specific parsing utilities.
 
-use super::{Shape, Slide};
+use super::{Shape, Slide, SlideTransition};
 use crate::common::{Error, Result, ShapeType};
 use quick_xml::Reader;
 use quick_xml::events::Event;
+use std::collections::HashMap;
 
 /// Parser for ODP-specific structures.
 ///
@@ -68,6 +69,14 @@ impl OdpParser {
         let mut in_slide = false;
         let mut slide_index = 0;
 
+        // Speaker notes and transition state
+        let mut current_notes: Vec<String> = Vec::new();
+        let mut in_notes = false;
+        let mut current_transition: Option<SlideTransition> = None;
+        let mut page_transitions: HashMap<String, SlideTransition> = HashMap::new();
+        let mut current_page_style: Option<String> = None;
+        let mut sequence_depth = 0usize;
+
         // Shape parsing state
         let mut current_shape: Option<ShapeBuilder> = None;
         let mut in_text_box = false;
@@ -86,7 +95,8 @@ impl OdpParser {
                                         .trim()
                                         .to_string(),
                                     index: slide_index,
-                                    notes: None,
+                                    notes: Self::join_notes(&mut current_notes),
+                                    transition: current_transition.take(),
                                     shapes: std::mem::take(&mut current_shapes),
                                 });
                                 slide_index += 1;
@@ -95,12 +105,43 @@ impl OdpParser {
                             // Start new slide
                             current_slide_title = Self::get_attr(e.attributes(), b"draw:name")
                                 .or_else(|| Some(format!("Slide{}", slide_index + 1)));
+                            current_transition = Self::get_attr(e.attributes(), b"draw:style-name")
+                                .and_then(|name| page_transitions.get(&name).cloned());
                             in_slide = true;
                         },
+                        b"style:style" => {
+                            if Self::get_attr(e.attributes(), b"style:family").as_deref()
+                                == Some("drawing-page")
+                            {
+                                current_page_style = Self::get_attr(e.attributes(), b"style:name");
+                            }
+                        },
+                        b"style:drawing-page-properties" => {
+                            if let Some(ref name) = current_page_style
+                                && let Some(transition) = SlideTransition::from_page_properties(e)
+                            {
+                                page_transitions.insert(name.clone(), transition);
+                            }
+                        },
+                        b"presentation:notes" => {
+                            in_notes = in_slide;
+                        },
+                        b"anim:seq" => {
+                            sequence_depth += 1;
+                        },
+                        b"anim:transitionFilter" => {
+                            // Effects inside an animation sequence are shape
+                            // animations, not the slide transition
+                            if in_slide && !in_notes && sequence_depth == 0 {
+                                current_transition
+                                    .get_or_insert_with(SlideTransition::default)
+                                    .apply_transition_filter(e);
+                            }
+                        },
                         b"draw:frame" | b"draw:rect" | b"draw:ellipse" | b"draw:line"
                         | b"draw:custom-shape" | b"draw:circle" | b"draw:path"
                         | b"draw:polygon" | b"draw:polyline" => {
-                            if in_slide && current_shape.is_none() {
+                            if in_slide && !in_notes && current_shape.is_none() {
                                 let mut builder = ShapeBuilder::new();
 
                                 // Determine shape type
@@ -154,16 +195,49 @@ impl OdpParser {
                                 in_text_box = true;
                             }
                         },
-                        b"text:p" | b"text:span" => {
+                        b"text:p" => {
+                            // Each notes paragraph becomes its own line
+                            if in_notes {
+                                current_notes.push(String::new());
+                            }
+                        },
+                        b"text:span" => {
                             // Text will be collected in Text event
                         },
                         _ => {},
                     }
                 },
+                Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                    b"style:drawing-page-properties" => {
+                        if let Some(ref name) = current_page_style
+                            && let Some(transition) = SlideTransition::from_page_properties(e)
+                        {
+                            page_transitions.insert(name.clone(), transition);
+                        }
+                    },
+                    b"anim:transitionFilter" => {
+                        if in_slide && !in_notes && sequence_depth == 0 {
+                            current_transition
+                                .get_or_insert_with(SlideTransition::default)
+                                .apply_transition_filter(e);
+                        }
+                    },
+                    _ => {},
+                },
                 Ok(Event::Text(ref t)) => {
                     if in_slide && let Ok(text) = String::from_utf8(t.to_vec()) {
                         let trimmed = text.trim();
-                        if !trimmed.is_empty() {
+                        if in_notes {
+                            match current_notes.last_mut() {
+                                _ if trimmed.is_empty() => {},
+                                Some(paragraph) if !paragraph.is_empty() => {
+                                    paragraph.push(' ');
+                                    paragraph.push_str(trimmed);
+                                },
+                                Some(paragraph) => paragraph.push_str(trimmed),
+                                None => current_notes.push(trimmed.to_string()),
+                            }
+                        } else if !trimmed.is_empty() {
                             // Add to slide text
                             if !current_slide_text.is_empty() {
                                 current_slide_text.push(' ');
@@ -193,12 +267,23 @@ impl OdpParser {
                                         .trim()
                                         .to_string(),
                                     index: slide_index,
-                                    notes: None,
+                                    notes: Self::join_notes(&mut current_notes),
+                                    transition: current_transition.take(),
                                     shapes: std::mem::take(&mut current_shapes),
                                 });
                                 slide_index += 1;
                             }
                             in_slide = false;
+                            in_notes = false;
+                        },
+                        b"style:style" => {
+                            current_page_style = None;
+                        },
+                        b"presentation:notes" => {
+                            in_notes = false;
+                        },
+                        b"anim:seq" => {
+                            sequence_depth = sequence_depth.saturating_sub(1);
                         },
                         b"draw:frame" | b"draw:rect" | b"draw:ellipse" | b"draw:line"
                         | b"draw:custom-shape" | b"draw:circle" | b"draw:path"
@@ -219,7 +304,12 @@ impl OdpParser {
                 },
                 Ok(Event::Eof) => break,
                 Err(e) => {
-                    return Err(Error::InvalidFormat(format!("XML parsing error: {}", e)));
+                    return Err(Error::xml_at(
+                        Some("content.xml"),
+                        xml_content.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
                 },
                 _ => {},
             }
@@ -229,6 +319,16 @@ impl OdpParser {
         Ok(slides)
     }
 
+    /// Join the collected notes paragraphs, skipping empty ones.
+    fn join_notes(paragraphs: &mut Vec<String>) -> Option<String> {
+        let notes = std::mem::take(paragraphs)
+            .into_iter()
+            .filter(|p| !p.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+        (!notes.is_empty()).then_some(notes)
+    }
+
     /// Helper to extract attribute values
     fn get_attr(attrs: quick_xml::events::attributes::Attributes, name: &[u8]) -> Option<String> {
         for attr_result in attrs {
@@ -323,6 +423,90 @@ mod tests {
         assert_eq!(slides[1].index, 1);
     }
 
+    const TEST_NOTES_TRANSITIONS_XML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0"
+    xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    xmlns:svg="urn:oasis:names:tc:opendocument:xmlns:svg-compatible:1.0"
+    xmlns:smil="urn:oasis:names:tc:opendocument:xmlns:smil-compatible:1.0"
+    xmlns:anim="urn:oasis:names:tc:opendocument:xmlns:animation:1.0"
+    xmlns:presentation="urn:oasis:names:tc:opendocument:xmlns:presentation:1.0">
+    <office:automatic-styles>
+        <style:style style:name="dp1" style:family="drawing-page">
+            <style:drawing-page-properties presentation:transition-speed="fast" smil:type="pushWipe" smil:subtype="fromRight"/>
+        </style:style>
+        <style:style style:name="dp2" style:family="drawing-page">
+            <style:drawing-page-properties presentation:transition-type="automatic" presentation:duration="PT00H00M03S" presentation:transition-style="dissolve"/>
+        </style:style>
+    </office:automatic-styles>
+    <office:body>
+        <office:presentation>
+            <draw:page draw:name="Intro" draw:style-name="dp1">
+                <draw:frame presentation:class="title"><draw:text-box><text:p>Welcome</text:p></draw:text-box></draw:frame>
+                <anim:par presentation:node-type="timing-root">
+                    <anim:par smil:begin="page1.begin">
+                        <anim:transitionFilter smil:dur="0.8s" smil:type="pushWipe" smil:subtype="fromRight"/>
+                    </anim:par>
+                    <anim:seq presentation:node-type="main-sequence">
+                        <anim:transitionFilter smil:dur="1s" smil:type="fade" smil:subtype="crossfade"/>
+                    </anim:seq>
+                </anim:par>
+                <presentation:notes>
+                    <draw:page-thumbnail draw:page-number="1" presentation:class="page"/>
+                    <draw:frame presentation:class="notes">
+                        <draw:text-box>
+                            <text:p>Greet the <text:span>audience</text:span></text:p>
+                            <text:p>Mention the agenda</text:p>
+                        </draw:text-box>
+                    </draw:frame>
+                </presentation:notes>
+            </draw:page>
+            <draw:page draw:name="Outro" draw:style-name="dp2">
+                <draw:frame><draw:text-box><text:p>Thanks</text:p></draw:text-box></draw:frame>
+                <presentation:notes>
+                    <draw:frame presentation:class="notes"><draw:text-box/></draw:frame>
+                </presentation:notes>
+            </draw:page>
+        </office:presentation>
+    </office:body>
+</office:document-content>"#;
+
+    #[test]
+    fn test_parse_notes_and_transitions() {
+        use crate::odf::odp::{TransitionDirection, TransitionSpeed, TransitionType};
+
+        let slides = OdpParser::parse_slides(TEST_NOTES_TRANSITIONS_XML).unwrap();
+        assert_eq!(slides.len(), 2);
+
+        // Notes are kept out of the slide text and shapes
+        assert_eq!(slides[0].text, "Welcome");
+        assert_eq!(slides[0].shapes.len(), 1);
+        assert_eq!(
+            slides[0].notes_text().unwrap(),
+            "Greet the audience\nMention the agenda"
+        );
+        assert_eq!(slides[1].notes, None);
+        assert_eq!(slides[1].notes_text().unwrap(), "");
+
+        // The timing-root filter supplies the duration; the one in the main
+        // sequence is a shape animation and is ignored
+        let intro = slides[0].transition().unwrap();
+        assert_eq!(
+            intro.transition_type,
+            TransitionType::Push {
+                direction: TransitionDirection::Right
+            }
+        );
+        assert_eq!(intro.speed, TransitionSpeed::Fast);
+        assert_eq!(intro.duration_ms, Some(800));
+        assert_eq!(intro.advance_after_ms, None);
+
+        let outro = slides[1].transition().unwrap();
+        assert_eq!(outro.transition_type, TransitionType::Dissolve);
+        assert_eq!(outro.advance_after_ms, Some(3000));
+    }
+
     #[test]
     fn test_parse_empty_presentation() {
         let slides = OdpParser::parse_slides(TEST_EMPTY_PRESENTATION).unwrap();
@@ -347,6 +531,7 @@ mod tests {
             text: "Content".to_string(),
             index: 0,
             notes: None,
+            transition: None,
             shapes: vec![],
         };
         let debug_str = format!("{:?}", slide);
@@ -361,6 +546,7 @@ mod tests {
             text: "Content".to_string(),
             index: 0,
             notes: None,
+            transition: None,
             shapes: vec![],
         };
         let cloned = slide.clone();
@@ -1,7 +1,7 @@
 //! Main Presentation structure and implementation.
 
 use super::Slide;
-use crate::common::{Error, Metadata, Result};
+use crate::common::{Error, Metadata, Protection, Result};
 use crate::odf::core::{Content, Meta, OwnedPackage, Styles};
 use std::path::Path;
 
@@ -86,7 +86,49 @@ impl Presentation {
     /// # }
     /// ```
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
-        let owned_package = OwnedPackage::from_bytes(bytes)?;
+        Self::from_package(OwnedPackage::from_bytes(bytes)?)
+    }
+
+    /// Open a password-protected ODP presentation.
+    ///
+    /// Entries encrypted with Blowfish CFB or AES-CBC, as written by
+    /// OpenOffice.org and LibreOffice, are decrypted in memory. Files that are
+    /// not encrypted open normally and the password is ignored.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::IncorrectPassword`] if the password does not decrypt the file
+    /// - [`Error::UnsupportedEncryption`] for other encryption schemes, such as
+    ///   the Argon2id and AES-GCM encryption of LibreOffice 24.2 and later
+    /// - [`Error::CorruptedFile`] if an encrypted entry is damaged
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use litchi::odf::Presentation;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let presentation = Presentation::open_with_password("protected.odp", "secret")?;
+    /// println!("{}", presentation.text()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "odf_encryption")]
+    pub fn open_with_password<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        let bytes = std::fs::read(path.as_ref())?;
+        Self::from_bytes_with_password(bytes, password)
+    }
+
+    /// Create a Presentation from the bytes of a possibly password-protected file.
+    ///
+    /// See [`Presentation::open_with_password`] for details.
+    #[cfg(feature = "odf_encryption")]
+    pub fn from_bytes_with_password(bytes: Vec<u8>, password: &str) -> Result<Self> {
+        Self::from_package(OwnedPackage::from_bytes_with_password(bytes, password)?)
+    }
+
+    /// Parse the parts of an opened package.
+    fn from_package(owned_package: OwnedPackage) -> Result<Self> {
         let package = owned_package.package()?;
 
         // Verify this is a presentation
@@ -190,6 +232,18 @@ impl Presentation {
         }
     }
 
+    /// Get the editing protection of the presentation.
+    ///
+    /// Reads the `LoadReadonly` recommendation and the change recording and
+    /// modify passwords from settings.xml.
+    pub fn protection(&self) -> Result<Protection> {
+        Ok(self
+            .package
+            .settings()?
+            .map(|settings| settings.protection())
+            .unwrap_or_default())
+    }
+
     // Note: For presentation modification operations, see `MutablePresentation` which provides
     // full CRUD operations on slides and shapes including add/remove/update slides, add/remove
     // shapes, and clear operations.
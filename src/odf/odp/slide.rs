@@ -1,5 +1,6 @@
 //! Slide and shape structures for ODP presentations.
 
+use super::SlideTransition;
 use crate::common::Result;
 
 /// A slide in an ODP presentation.
@@ -15,6 +16,8 @@ pub struct Slide {
     pub index: usize,
     /// Optional notes for the slide
     pub notes: Option<String>,
+    /// Transition played when the slide is shown
+    pub transition: Option<SlideTransition>,
     /// Shapes on the slide
     pub shapes: Vec<Shape>,
 }
@@ -54,6 +57,22 @@ impl Slide {
     pub fn notes(&self) -> Result<Option<&str>> {
         Ok(self.notes.as_deref())
     }
+
+    /// Extract the speaker notes text of the slide.
+    ///
+    /// Returns the text of the `presentation:notes` page, one line per
+    /// paragraph, or an empty string if the slide has no notes.
+    pub fn notes_text(&self) -> Result<&str> {
+        Ok(self.notes.as_deref().unwrap_or_default())
+    }
+
+    /// Get the slide transition.
+    ///
+    /// Returns the transition from the slide's drawing-page style if present,
+    /// None otherwise.
+    pub fn transition(&self) -> Option<&SlideTransition> {
+        self.transition.as_ref()
+    }
 }
 
 /// A shape (element) on a slide.
@@ -142,6 +161,7 @@ mod tests {
             text: String::new(),
             index: 0,
             notes: None,
+            transition: None,
             shapes: vec![],
         };
         assert!(slide.title.is_none());
@@ -155,6 +175,7 @@ mod tests {
             text: "Slide content".to_string(),
             index: 5,
             notes: Some("Speaker notes".to_string()),
+            transition: None,
             shapes: vec![],
         };
         assert_eq!(slide.title().unwrap(), Some("Test Slide"));
@@ -170,6 +191,7 @@ mod tests {
             text: String::new(),
             index: 0,
             notes: None,
+            transition: None,
             shapes: vec![],
         };
         assert_eq!(slide.title().unwrap(), Some("Title"));
@@ -182,6 +204,7 @@ mod tests {
             text: String::new(),
             index: 0,
             notes: None,
+            transition: None,
             shapes: vec![],
         };
         assert_eq!(slide.title().unwrap(), None);
@@ -194,6 +217,7 @@ mod tests {
             text: "Hello World".to_string(),
             index: 0,
             notes: None,
+            transition: None,
             shapes: vec![],
         };
         assert_eq!(slide.text().unwrap(), "Hello World");
@@ -216,6 +240,7 @@ mod tests {
             text: String::new(),
             index: 0,
             notes: None,
+            transition: None,
             shapes,
         };
         assert_eq!(slide.shapes().unwrap().len(), 1);
@@ -228,6 +253,7 @@ mod tests {
             text: String::new(),
             index: 42,
             notes: None,
+            transition: None,
             shapes: vec![],
         };
         assert_eq!(slide.index(), 42);
@@ -240,6 +266,7 @@ mod tests {
             text: String::new(),
             index: 0,
             notes: Some("Notes".to_string()),
+            transition: None,
             shapes: vec![],
         };
         assert_eq!(slide.notes().unwrap(), Some("Notes"));
@@ -252,6 +279,7 @@ mod tests {
             text: String::new(),
             index: 0,
             notes: None,
+            transition: None,
             shapes: vec![],
         };
         assert_eq!(slide.notes().unwrap(), None);
@@ -264,6 +292,7 @@ mod tests {
             text: "Content".to_string(),
             index: 1,
             notes: Some("Notes".to_string()),
+            transition: None,
             shapes: vec![],
         };
         let cloned = slide.clone();
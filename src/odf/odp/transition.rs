@@ -0,0 +1,490 @@
+//! Slide transition effects for ODP presentations.
+//!
+//! ODF stores a slide's transition on the drawing-page style referenced by its
+//! `draw:page`. Older files use the ODF 1.0 `presentation:transition-style`
+//! names, newer ones the SMIL `smil:type`/`smil:subtype`/`smil:direction`
+//! triple, which LibreOffice also repeats as an `anim:transitionFilter` in the
+//! page's timing tree. Both are mapped onto the same types, which mirror
+//! `ooxml::pptx::SlideTransition`.
+
+use crate::odf::datatype::DurationOdf;
+use quick_xml::events::BytesStart;
+
+/// Slide transition type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransitionType {
+    /// No transition
+    None,
+    /// Fade (cross-fade or through a color)
+    Fade,
+    /// Push transition
+    Push { direction: TransitionDirection },
+    /// Wipe transition
+    Wipe { direction: TransitionDirection },
+    /// Split (barn door) transition
+    Split { direction: TransitionDirection },
+    /// Random bars
+    RandomBars { direction: TransitionDirection },
+    /// Cover transition
+    Cover { direction: TransitionDirection },
+    /// Uncover transition
+    Uncover { direction: TransitionDirection },
+    /// Dissolve transition
+    Dissolve,
+    /// Checkerboard
+    Checker { direction: TransitionDirection },
+    /// Blinds
+    Blinds { direction: TransitionDirection },
+    /// Clock sweep
+    Clock { direction: ClockDirection },
+    /// Zoom (in/out)
+    Zoom { direction: ZoomDirection },
+    /// Random transition
+    Random,
+    /// Wheel (spokes)
+    Wheel { spokes: u8 },
+    /// Circle transition
+    Circle,
+    /// Diamond transition
+    Diamond,
+    /// Plus transition
+    Plus,
+    /// Wedge transition
+    Wedge,
+    /// Newsflash transition
+    Newsflash,
+    /// Comb transition
+    Comb { direction: TransitionDirection },
+    /// Other transition, holding the SMIL `type:subtype` or ODF 1.0 style name
+    Other(String),
+}
+
+/// Transition direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionDirection {
+    /// Left to right
+    Left,
+    /// Right to left
+    Right,
+    /// Top to bottom
+    Up,
+    /// Bottom to top
+    Down,
+    /// Horizontal (left and right)
+    Horizontal,
+    /// Vertical (up and down)
+    Vertical,
+}
+
+/// Clock transition direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockDirection {
+    /// Clockwise
+    Clockwise,
+    /// Counterclockwise
+    Counterclockwise,
+}
+
+/// Zoom transition direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZoomDirection {
+    /// Zoom in
+    In,
+    /// Zoom out
+    Out,
+}
+
+/// Transition speed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionSpeed {
+    /// Slow transition (1500ms)
+    Slow,
+    /// Medium transition (1000ms)
+    Medium,
+    /// Fast transition (500ms)
+    Fast,
+}
+
+impl TransitionSpeed {
+    /// Get the duration in milliseconds.
+    pub fn duration_ms(&self) -> u32 {
+        match self {
+            TransitionSpeed::Slow => 1500,
+            TransitionSpeed::Medium => 1000,
+            TransitionSpeed::Fast => 500,
+        }
+    }
+
+    /// Parse a `presentation:transition-speed` value.
+    fn from_odf_value(value: &str) -> Self {
+        match value {
+            "slow" => TransitionSpeed::Slow,
+            "fast" => TransitionSpeed::Fast,
+            _ => TransitionSpeed::Medium,
+        }
+    }
+}
+
+/// Complete slide transition configuration.
+///
+/// Includes the transition type, speed, and timing settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlideTransition {
+    /// Type of transition effect
+    pub transition_type: TransitionType,
+    /// Speed of the transition
+    pub speed: TransitionSpeed,
+    /// Duration in milliseconds (optional, overrides speed)
+    pub duration_ms: Option<u32>,
+    /// Whether to advance slide on mouse click
+    pub advance_on_click: bool,
+    /// Auto-advance after delay in milliseconds (None = no auto-advance)
+    pub advance_after_ms: Option<u32>,
+}
+
+impl Default for SlideTransition {
+    fn default() -> Self {
+        Self {
+            transition_type: TransitionType::None,
+            speed: TransitionSpeed::Medium,
+            duration_ms: None,
+            advance_on_click: true,
+            advance_after_ms: None,
+        }
+    }
+}
+
+impl SlideTransition {
+    /// Get the effective duration in milliseconds.
+    pub fn effective_duration_ms(&self) -> u32 {
+        self.duration_ms.unwrap_or_else(|| self.speed.duration_ms())
+    }
+
+    /// Read the transition from a `style:drawing-page-properties` element.
+    ///
+    /// Returns `None` when the element carries no transition attributes.
+    pub(crate) fn from_page_properties(element: &BytesStart<'_>) -> Option<Self> {
+        let mut transition = Self::default();
+        let mut found = false;
+        let mut style = None;
+        let mut smil = SmilEffect::default();
+        let mut automatic = false;
+        let mut page_duration = None;
+
+        for attr in element.attributes().flatten() {
+            let Ok(value) = std::str::from_utf8(&attr.value) else {
+                continue;
+            };
+            match attr.key.as_ref() {
+                b"presentation:transition-type" => {
+                    automatic = value == "automatic";
+                    found = true;
+                },
+                b"presentation:transition-style" => {
+                    style = Some(value.to_string());
+                    found = true;
+                },
+                b"presentation:transition-speed" => {
+                    transition.speed = TransitionSpeed::from_odf_value(value);
+                    found = true;
+                },
+                b"presentation:duration" => {
+                    page_duration = DurationOdf::decode(value)
+                        .ok()
+                        .and_then(|d| u32::try_from(d.num_milliseconds()).ok());
+                },
+                _ => found |= smil.read_attribute(attr.key.as_ref(), value),
+            }
+        }
+
+        if !found {
+            return None;
+        }
+
+        if automatic {
+            transition.advance_after_ms = Some(page_duration.unwrap_or(0));
+        }
+        transition.duration_ms = smil.duration_ms;
+        transition.transition_type = match (&smil.kind, style) {
+            (Some(_), _) => smil.transition_type(),
+            (None, Some(style)) => Self::type_from_style(&style),
+            (None, None) => TransitionType::None,
+        };
+
+        Some(transition)
+    }
+
+    /// Apply an `anim:transitionFilter` element from the page's timing tree.
+    pub(crate) fn apply_transition_filter(&mut self, element: &BytesStart<'_>) {
+        let mut smil = SmilEffect::default();
+        for attr in element.attributes().flatten() {
+            if let Ok(value) = std::str::from_utf8(&attr.value) {
+                smil.read_attribute(attr.key.as_ref(), value);
+            }
+        }
+
+        if smil.kind.is_some() {
+            self.transition_type = smil.transition_type();
+        }
+        if smil.duration_ms.is_some() {
+            self.duration_ms = smil.duration_ms;
+        }
+    }
+
+    /// Map an ODF 1.0 `presentation:transition-style` name.
+    fn type_from_style(style: &str) -> TransitionType {
+        use TransitionDirection::*;
+
+        let side = |name: &str| match name {
+            "left" => Some(Left),
+            "right" => Some(Right),
+            "top" => Some(Up),
+            "bottom" => Some(Down),
+            _ => None,
+        };
+
+        if let Some(direction) = style.strip_prefix("fade-from-").and_then(side) {
+            return TransitionType::Wipe { direction };
+        }
+        if let Some(direction) = style.strip_prefix("move-from-").and_then(side) {
+            return TransitionType::Cover { direction };
+        }
+        if let Some(direction) = style.strip_prefix("uncover-to-").and_then(side) {
+            return TransitionType::Uncover { direction };
+        }
+        if let Some(direction) = style.strip_prefix("roll-from-").and_then(side) {
+            return TransitionType::Push { direction };
+        }
+
+        match style {
+            "none" => TransitionType::None,
+            "clockwise" => TransitionType::Clock {
+                direction: ClockDirection::Clockwise,
+            },
+            "counterclockwise" => TransitionType::Clock {
+                direction: ClockDirection::Counterclockwise,
+            },
+            "vertical-stripes" => TransitionType::Blinds {
+                direction: Vertical,
+            },
+            "horizontal-stripes" => TransitionType::Blinds {
+                direction: Horizontal,
+            },
+            "vertical-lines" => TransitionType::RandomBars {
+                direction: Vertical,
+            },
+            "horizontal-lines" => TransitionType::RandomBars {
+                direction: Horizontal,
+            },
+            "vertical-checkerboard" => TransitionType::Checker {
+                direction: Vertical,
+            },
+            "horizontal-checkerboard" => TransitionType::Checker {
+                direction: Horizontal,
+            },
+            "open-vertical" | "close-vertical" => TransitionType::Split {
+                direction: Vertical,
+            },
+            "open-horizontal" | "close-horizontal" => TransitionType::Split {
+                direction: Horizontal,
+            },
+            "dissolve" => TransitionType::Dissolve,
+            "random" => TransitionType::Random,
+            other => TransitionType::Other(other.to_string()),
+        }
+    }
+}
+
+/// The SMIL attributes describing a transition effect.
+#[derive(Default)]
+struct SmilEffect {
+    kind: Option<String>,
+    subtype: Option<String>,
+    reverse: bool,
+    duration_ms: Option<u32>,
+}
+
+impl SmilEffect {
+    /// Record a `smil:*` attribute, returning whether it was one.
+    fn read_attribute(&mut self, key: &[u8], value: &str) -> bool {
+        match key {
+            b"smil:type" => self.kind = Some(value.to_string()),
+            b"smil:subtype" => self.subtype = Some(value.to_string()),
+            b"smil:direction" => self.reverse = value == "reverse",
+            b"smil:dur" => self.duration_ms = parse_clock_value(value),
+            _ => return false,
+        }
+        true
+    }
+
+    /// Map the SMIL type and subtype onto a transition type.
+    fn transition_type(&self) -> TransitionType {
+        use TransitionDirection::*;
+
+        let kind = self.kind.as_deref().unwrap_or_default();
+        let subtype = self.subtype.as_deref().unwrap_or_default();
+        let from = match subtype {
+            "fromLeft" => Some(Left),
+            "fromRight" => Some(Right),
+            "fromTop" => Some(Up),
+            "fromBottom" => Some(Down),
+            _ => None,
+        };
+        let axis = match subtype {
+            "vertical" | "down" | "combVertical" => Vertical,
+            _ => Horizontal,
+        };
+
+        match (kind, from) {
+            ("fade", _) => TransitionType::Fade,
+            ("dissolve", _) => TransitionType::Dissolve,
+            ("random", _) => TransitionType::Random,
+            ("barWipe", _) => TransitionType::Wipe {
+                direction: match (subtype, self.reverse) {
+                    ("topToBottom", false) => Up,
+                    ("topToBottom", true) => Down,
+                    (_, false) => Left,
+                    (_, true) => Right,
+                },
+            },
+            ("pushWipe", Some(direction)) => TransitionType::Push { direction },
+            ("pushWipe", None) => TransitionType::Comb { direction: axis },
+            ("slideWipe", Some(direction)) if self.reverse => TransitionType::Uncover { direction },
+            ("slideWipe", Some(direction)) => TransitionType::Cover { direction },
+            ("barnDoorWipe", _) => TransitionType::Split { direction: axis },
+            ("blindsWipe", _) => TransitionType::Blinds { direction: axis },
+            ("checkerBoardWipe", _) => TransitionType::Checker { direction: axis },
+            ("randomBarWipe", _) => TransitionType::RandomBars { direction: axis },
+            ("clockWipe", _) => TransitionType::Clock {
+                direction: if self.reverse {
+                    ClockDirection::Counterclockwise
+                } else {
+                    ClockDirection::Clockwise
+                },
+            },
+            ("pinWheelWipe", _) => TransitionType::Wheel {
+                spokes: match subtype {
+                    "oneBlade" => 1,
+                    "threeBlade" => 3,
+                    "fourBlade" => 4,
+                    "eightBlade" => 8,
+                    _ => 2,
+                },
+            },
+            ("fanWipe", _) => TransitionType::Wedge,
+            ("ellipseWipe", _) => TransitionType::Circle,
+            ("irisWipe", _) if subtype == "diamond" => TransitionType::Diamond,
+            ("fourBoxWipe", _) if subtype == "cornersOut" => TransitionType::Plus,
+            ("zoom", _) if subtype == "rotateIn" => TransitionType::Newsflash,
+            ("zoom", _) => TransitionType::Zoom {
+                direction: if self.reverse {
+                    ZoomDirection::Out
+                } else {
+                    ZoomDirection::In
+                },
+            },
+            _ if subtype.is_empty() => TransitionType::Other(kind.to_string()),
+            _ => TransitionType::Other(format!("{kind}:{subtype}")),
+        }
+    }
+}
+
+/// Parse a SMIL clock value such as `2s`, `500ms` or `PT2S` into milliseconds.
+fn parse_clock_value(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let seconds = if let Some(ms) = value.strip_suffix("ms") {
+        ms.parse::<f64>().ok()? / 1000.0
+    } else if let Some(s) = value.strip_suffix('s') {
+        s.parse::<f64>().ok()?
+    } else if value.starts_with('P') {
+        return DurationOdf::decode(value)
+            .ok()
+            .and_then(|d| u32::try_from(d.num_milliseconds()).ok());
+    } else {
+        value.parse::<f64>().ok()?
+    };
+
+    (seconds >= 0.0).then(|| (seconds * 1000.0).round() as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quick_xml::Reader;
+    use quick_xml::events::Event;
+
+    fn element(xml: &str) -> BytesStart<'static> {
+        let mut reader = Reader::from_str(xml);
+        match reader.read_event().unwrap() {
+            Event::Empty(e) | Event::Start(e) => e.into_owned(),
+            other => panic!("unexpected event {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_smil_page_properties() {
+        let props = element(
+            r#"<style:drawing-page-properties presentation:transition-type="automatic" presentation:duration="PT00H00M05S" presentation:transition-speed="fast" smil:type="slideWipe" smil:subtype="fromTop" smil:direction="reverse"/>"#,
+        );
+        let transition = SlideTransition::from_page_properties(&props).unwrap();
+
+        assert_eq!(
+            transition.transition_type,
+            TransitionType::Uncover {
+                direction: TransitionDirection::Up
+            }
+        );
+        assert_eq!(transition.speed, TransitionSpeed::Fast);
+        assert_eq!(transition.advance_after_ms, Some(5000));
+        assert_eq!(transition.effective_duration_ms(), 500);
+    }
+
+    #[test]
+    fn test_legacy_transition_style() {
+        let props = element(
+            r#"<style:drawing-page-properties presentation:transition-style="vertical-checkerboard" presentation:transition-speed="slow"/>"#,
+        );
+        let transition = SlideTransition::from_page_properties(&props).unwrap();
+
+        assert_eq!(
+            transition.transition_type,
+            TransitionType::Checker {
+                direction: TransitionDirection::Vertical
+            }
+        );
+        assert_eq!(transition.speed, TransitionSpeed::Slow);
+        assert_eq!(transition.advance_after_ms, None);
+        assert!(transition.advance_on_click);
+    }
+
+    #[test]
+    fn test_no_transition_attributes() {
+        let props = element(
+            r#"<style:drawing-page-properties presentation:background-visible="true" presentation:display-footer="true"/>"#,
+        );
+        assert!(SlideTransition::from_page_properties(&props).is_none());
+    }
+
+    #[test]
+    fn test_transition_filter_overrides_type_and_duration() {
+        let mut transition = SlideTransition::default();
+        transition.apply_transition_filter(&element(
+            r#"<anim:transitionFilter smil:dur="2.5s" smil:type="pinWheelWipe" smil:subtype="fourBlade"/>"#,
+        ));
+
+        assert_eq!(
+            transition.transition_type,
+            TransitionType::Wheel { spokes: 4 }
+        );
+        assert_eq!(transition.duration_ms, Some(2500));
+    }
+
+    #[test]
+    fn test_parse_clock_value() {
+        assert_eq!(parse_clock_value("2s"), Some(2000));
+        assert_eq!(parse_clock_value("750ms"), Some(750));
+        assert_eq!(parse_clock_value("0.25"), Some(250));
+        assert_eq!(parse_clock_value("PT2S"), Some(2000));
+        assert_eq!(parse_clock_value("soon"), None);
+    }
+}
@@ -2,9 +2,11 @@
 //!
 //! This module provides a builder pattern for creating new ODS spreadsheets from scratch.
 
-use crate::common::{Metadata, Result, xml::escape_xml};
-use crate::odf::core::{OdfStructure, PackageWriter};
-use crate::odf::ods::{Cell, CellValue, Row, Sheet};
+use super::named_range::named_expressions_xml;
+use crate::common::{CustomPropertyValue, Metadata, Result, xml::escape_xml};
+use crate::odf::coordinates::CellCoord;
+use crate::odf::core::{OdfStructure, PackageWriter, meta_xml};
+use crate::odf::ods::{Cell, CellComment, CellValue, NamedRange, Row, Sheet, SheetProtection};
 use std::path::Path;
 
 /// Builder for creating new ODS spreadsheets.
@@ -28,6 +30,7 @@ use std::path::Path;
 /// ```
 pub struct SpreadsheetBuilder {
     sheets: Vec<Sheet>,
+    named_ranges: Vec<NamedRange>,
     metadata: Metadata,
 }
 
@@ -50,6 +53,7 @@ impl SpreadsheetBuilder {
     pub fn new() -> Self {
         Self {
             sheets: Vec::new(),
+            named_ranges: Vec::new(),
             metadata: Metadata::default(),
         }
     }
@@ -63,6 +67,43 @@ impl SpreadsheetBuilder {
         self.metadata = metadata;
     }
 
+    /// Get a mutable reference to the document metadata
+    ///
+    /// Every field written to meta.xml can be set here, including the
+    /// language and the revision, which is written as the editing cycles.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Set a user-defined metadata field
+    ///
+    /// The field is written as a `meta:user-defined` element whose value type
+    /// (string, float, date or boolean) follows the value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the field
+    /// * `value` - Value of the field
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use litchi::odf::SpreadsheetBuilder;
+    ///
+    /// let mut builder = SpreadsheetBuilder::new();
+    /// builder.set_user_defined("DocumentId", "DOC-0042");
+    /// builder.set_user_defined("Reviewed", true);
+    /// ```
+    pub fn set_user_defined(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<CustomPropertyValue>,
+    ) {
+        self.metadata
+            .custom_properties
+            .insert(name.into(), value.into());
+    }
+
     /// Add a new sheet to the spreadsheet
     ///
     /// # Arguments
@@ -83,7 +124,7 @@ impl SpreadsheetBuilder {
     pub fn add_sheet(&mut self, name: &str) -> Result<&mut Self> {
         let sheet = Sheet {
             name: name.to_string(),
-            rows: Vec::new(),
+            ..Default::default()
         };
         self.sheets.push(sheet);
         Ok(self)
@@ -390,6 +431,146 @@ impl SpreadsheetBuilder {
         Ok(self)
     }
 
+    /// Define a named range
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the range
+    /// * `range` - ODF cell range address (e.g., "$Sheet1.$A$1:.$B$10") or
+    ///   Excel-style range (e.g., "Sheet1!$A$1:$B$10")
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use litchi::odf::SpreadsheetBuilder;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let mut builder = SpreadsheetBuilder::new();
+    /// builder.add_sheet("Sheet1")?;
+    /// builder.add_named_range("Prices", "Sheet1!$B$2:$B$20")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn add_named_range(&mut self, name: &str, range: &str) -> Result<&mut Self> {
+        if name.is_empty() || range.trim().is_empty() {
+            return Err(crate::Error::Other(
+                "Named range requires a name and a range".to_string(),
+            ));
+        }
+        if self.named_ranges.iter().any(|r| r.name == name) {
+            return Err(crate::Error::Other(format!(
+                "Named range '{}' already exists",
+                name
+            )));
+        }
+
+        self.named_ranges.push(NamedRange::new(name, range));
+        Ok(self)
+    }
+
+    /// Attach a comment to a cell
+    ///
+    /// The comment is dated with the current time. Setting a comment on a
+    /// cell that already has one replaces it.
+    ///
+    /// # Arguments
+    ///
+    /// * `sheet` - Name of the sheet
+    /// * `a1` - Cell address in A1 notation (e.g., "B2")
+    /// * `text` - Comment text; newlines separate paragraphs
+    /// * `author` - Author of the comment
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use litchi::odf::SpreadsheetBuilder;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let mut builder = SpreadsheetBuilder::new();
+    /// builder.add_sheet("Sheet1")?;
+    /// builder.add_row_with_numbers(&[42.0])?;
+    /// builder.set_cell_comment("Sheet1", "A1", "Checked against invoice", "Alice")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_cell_comment(
+        &mut self,
+        sheet: &str,
+        a1: &str,
+        text: &str,
+        author: &str,
+    ) -> Result<&mut Self> {
+        let coord: CellCoord = a1.parse()?;
+        let (row, col) = (coord.row(), coord.column());
+        let sheet = self.sheet_mut(sheet)?;
+
+        // Ensure the cell exists so the comment has an element to live in
+        while sheet.rows.len() <= row {
+            sheet.rows.push(Row {
+                cells: Vec::new(),
+                index: sheet.rows.len(),
+            });
+        }
+        let row_data = &mut sheet.rows[row];
+        while row_data.cells.len() <= col {
+            row_data.cells.push(Cell {
+                text: String::new(),
+                value: CellValue::Empty,
+                formula: None,
+                row,
+                col: row_data.cells.len(),
+            });
+        }
+
+        sheet.comments.retain(|c| c.row != row || c.col != col);
+        sheet.comments.push(CellComment {
+            author: Some(author.to_string()),
+            date: Some(chrono::Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string()),
+            ..CellComment::new(row, col, text)
+        });
+
+        Ok(self)
+    }
+
+    /// Protect a sheet with a password
+    ///
+    /// The password is stored as the legacy SHA-1 protection key of the ODF
+    /// specification. An empty password protects the sheet without a key.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the sheet
+    /// * `password` - Password required to unprotect the sheet
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use litchi::odf::SpreadsheetBuilder;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let mut builder = SpreadsheetBuilder::new();
+    /// builder.add_sheet("Sheet1")?;
+    /// builder.protect_sheet("Sheet1", "secret")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn protect_sheet(&mut self, name: &str, password: &str) -> Result<&mut Self> {
+        let sheet = self.sheet_mut(name)?;
+        sheet.protection = Some(if password.is_empty() {
+            SheetProtection::new()
+        } else {
+            SheetProtection::with_password(password)
+        });
+        Ok(self)
+    }
+
+    fn sheet_mut(&mut self, name: &str) -> Result<&mut Sheet> {
+        self.sheets
+            .iter_mut()
+            .find(|sheet| sheet.name == name)
+            .ok_or_else(|| crate::Error::Other(format!("Sheet '{}' not found", name)))
+    }
+
     /// Select a specific sheet by index for subsequent operations
     ///
     /// # Arguments
@@ -499,10 +680,16 @@ impl SpreadsheetBuilder {
             .any(|c| c.formula.is_some())
     }
 
-    fn push_table_start(out: &mut String, name: &str) {
+    fn push_table_start(out: &mut String, sheet: &Sheet) {
+        let protection = sheet
+            .protection
+            .as_ref()
+            .map(SheetProtection::table_attributes)
+            .unwrap_or_default();
         out.push_str(&format!(
-            r#"<table:table table:name="{}">"#,
-            escape_xml(name)
+            r#"<table:table table:name="{}"{}>"#,
+            escape_xml(&sheet.name),
+            protection
         ));
     }
 
@@ -517,7 +704,8 @@ impl SpreadsheetBuilder {
         }
     }
 
-    fn push_cell(out: &mut String, cell: &Cell) {
+    fn push_cell(out: &mut String, cell: &Cell, comment: Option<&CellComment>) {
+        let annotation = comment.map(CellComment::to_xml).unwrap_or_default();
         let formula_attr = cell
             .formula
             .as_deref()
@@ -527,14 +715,14 @@ impl SpreadsheetBuilder {
         match &cell.value {
             CellValue::Text(_) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="string">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     escape_xml(&cell.text)
                 ));
             },
             CellValue::Number(f) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="float" office:value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="float" office:value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     f,
                     escape_xml(&cell.text)
@@ -542,7 +730,7 @@ impl SpreadsheetBuilder {
             },
             CellValue::Currency(f, currency) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="currency" office:value="{}" office:currency="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="currency" office:value="{}" office:currency="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     f,
                     escape_xml(currency),
@@ -551,7 +739,7 @@ impl SpreadsheetBuilder {
             },
             CellValue::Percentage(f) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="percentage" office:value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="percentage" office:value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     f,
                     escape_xml(&cell.text)
@@ -559,7 +747,7 @@ impl SpreadsheetBuilder {
             },
             CellValue::Date(d) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="date" office:date-value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="date" office:date-value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     escape_xml(d),
                     escape_xml(&cell.text)
@@ -567,7 +755,7 @@ impl SpreadsheetBuilder {
             },
             CellValue::Time(t) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="time" office:time-value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="time" office:time-value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     escape_xml(t),
                     escape_xml(&cell.text)
@@ -575,7 +763,7 @@ impl SpreadsheetBuilder {
             },
             CellValue::Boolean(b) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="boolean" office:boolean-value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="boolean" office:boolean-value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     b,
                     escape_xml(&cell.text)
@@ -584,11 +772,15 @@ impl SpreadsheetBuilder {
             CellValue::Empty => {
                 if cell.formula.is_some() {
                     out.push_str(&format!(
-                        r#"<table:table-cell{} office:value-type="float" office:value="0"><text:p>0</text:p></table:table-cell>"#,
+                        r#"<table:table-cell{} office:value-type="float" office:value="0">{annotation}<text:p>0</text:p></table:table-cell>"#,
                         formula_attr
                     ));
-                } else {
+                } else if annotation.is_empty() {
                     out.push_str("<table:table-cell/>");
+                } else {
+                    out.push_str(&format!(
+                        "<table:table-cell>{annotation}</table:table-cell>"
+                    ));
                 }
             },
         }
@@ -618,13 +810,13 @@ impl SpreadsheetBuilder {
         let mut body = String::with_capacity(estimated);
 
         for sheet in &self.sheets {
-            Self::push_table_start(&mut body, &sheet.name);
+            Self::push_table_start(&mut body, sheet);
             Self::push_table_columns(&mut body, Self::sheet_max_cols(sheet));
 
-            for row in &sheet.rows {
+            for (row_index, row) in sheet.rows.iter().enumerate() {
                 body.push_str("<table:table-row>");
-                for cell in &row.cells {
-                    Self::push_cell(&mut body, cell);
+                for (col, cell) in row.cells.iter().enumerate() {
+                    Self::push_cell(&mut body, cell, sheet.comment(row_index, col));
                 }
                 body.push_str("</table:table-row>");
             }
@@ -632,6 +824,8 @@ impl SpreadsheetBuilder {
             body.push_str("</table:table>");
         }
 
+        body.push_str(&named_expressions_xml(&self.named_ranges));
+
         body
     }
 
@@ -647,7 +841,7 @@ impl SpreadsheetBuilder {
 
         let mut out = String::with_capacity(body.len() + 256);
         out.push_str(
-            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0""#,
+            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/""#,
         );
         out.push_str(of_ns);
         out.push_str(
@@ -659,26 +853,7 @@ impl SpreadsheetBuilder {
     }
 
     fn generate_meta_xml(&self) -> String {
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let mut meta = format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" office:version="1.3"><office:meta><meta:generator>Litchi/0.0.1</meta:generator><meta:creation-date>{}</meta:creation-date><dc:date>{}</dc:date>"#,
-            now, now
-        );
-
-        // Add optional metadata fields
-        if let Some(ref title) = self.metadata.title {
-            meta.push_str(&format!("<dc:title>{}</dc:title>", escape_xml(title)));
-        }
-
-        if let Some(ref author) = self.metadata.author {
-            meta.push_str(&format!("<dc:creator>{}</dc:creator>", escape_xml(author)));
-        }
-
-        meta.push_str("</office:meta>");
-        meta.push_str("</office:document-meta>");
-
-        meta
+        meta_xml(&self.metadata)
     }
 
     /// Build the spreadsheet and return as bytes
@@ -948,6 +1123,7 @@ mod tests {
         let sheet = Sheet {
             name: "CustomSheet".to_string(),
             rows: vec![],
+            ..Default::default()
         };
         builder.add_sheet_element(sheet).unwrap();
 
@@ -1041,6 +1217,35 @@ mod tests {
         assert!(meta_xml.contains("John Doe"));
     }
 
+    #[test]
+    fn test_metadata_read_back() {
+        use crate::common::CustomPropertyValue;
+        use crate::odf::{MutableSpreadsheet, Spreadsheet};
+
+        let due: chrono::DateTime<chrono::Utc> = "2025-01-31T00:00:00Z".parse().unwrap();
+        let mut builder = SpreadsheetBuilder::new();
+        builder.add_sheet("Sheet1").unwrap();
+        builder.metadata_mut().subject = Some("Budget".to_string());
+        builder.set_user_defined("Due", due);
+
+        // The mutable spreadsheet used to write meta.xml without any fields
+        let spreadsheet = Spreadsheet::from_bytes(builder.build().unwrap()).unwrap();
+        let mut mutable = MutableSpreadsheet::from_spreadsheet(spreadsheet).unwrap();
+        mutable.set_user_defined("Final", false);
+        let reloaded = Spreadsheet::from_bytes(mutable.to_bytes().unwrap()).unwrap();
+
+        let metadata = reloaded.metadata().unwrap();
+        assert_eq!(metadata.subject, Some("Budget".to_string()));
+        assert_eq!(
+            metadata.custom_properties.get("Due"),
+            Some(&CustomPropertyValue::DateTime(due))
+        );
+        assert_eq!(
+            metadata.custom_properties.get("Final"),
+            Some(&CustomPropertyValue::Bool(false))
+        );
+    }
+
     #[test]
     fn test_build() {
         let mut builder = SpreadsheetBuilder::new();
@@ -1125,4 +1330,55 @@ mod tests {
         // Empty cell with formula should have value type
         assert!(xml.contains("office:value-type="));
     }
+
+    #[test]
+    fn test_named_ranges_comments_and_protection() {
+        let mut builder = SpreadsheetBuilder::new();
+        builder.add_sheet("Data").unwrap();
+        builder.add_row_with_numbers(&[1.0, 2.0]).unwrap();
+        builder.add_named_range("Values", "Data!$A$1:$B$1").unwrap();
+        assert!(builder.add_named_range("Values", "Data!A1").is_err());
+        builder
+            .set_cell_comment("Data", "B1", "Second <value>", "Alice")
+            .unwrap();
+        builder
+            .set_cell_comment("Data", "C3", "Outside the data", "Bob")
+            .unwrap();
+        builder.protect_sheet("Data", "secret").unwrap();
+        assert!(builder.protect_sheet("Missing", "secret").is_err());
+        assert!(builder.set_cell_comment("Missing", "A1", "x", "y").is_err());
+
+        let xml = builder.generate_content_xml();
+        assert!(xml.contains(r#"table:protected="true""#));
+        assert!(xml.contains("<dc:creator>Alice</dc:creator>"));
+        assert!(xml.contains(r#"table:cell-range-address="$Data.$A$1:.$B$1""#));
+
+        let mut spreadsheet =
+            crate::odf::Spreadsheet::from_bytes(builder.build().unwrap()).unwrap();
+        let sheets = spreadsheet.sheets().unwrap();
+        let sheet = &sheets[0];
+
+        let comment = sheet.comment(0, 1).unwrap();
+        assert_eq!(comment.text, "Second <value>");
+        assert_eq!(comment.author.as_deref(), Some("Alice"));
+        assert!(comment.date.is_some());
+        assert_eq!(sheet.comment(2, 2).unwrap().text, "Outside the data");
+        assert_eq!(sheet.rows[0].cells[1].text, "2");
+
+        assert!(sheet.is_protected());
+        assert!(sheet.protection.as_ref().unwrap().verify_password("secret"));
+
+        let ranges = spreadsheet.named_ranges().unwrap();
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges[0].name, "Values");
+        assert_eq!(ranges[0].range, "$Data.$A$1:.$B$1");
+
+        // Modifying and saving the spreadsheet keeps all three
+        let mutable = crate::odf::MutableSpreadsheet::from_spreadsheet(spreadsheet).unwrap();
+        let mut reread = crate::odf::Spreadsheet::from_bytes(mutable.to_bytes().unwrap()).unwrap();
+        let sheets = reread.sheets().unwrap();
+        assert_eq!(sheets[0].comments, sheet.comments);
+        assert_eq!(sheets[0].protection, sheet.protection);
+        assert_eq!(reread.named_ranges().unwrap(), ranges);
+    }
 }
@@ -0,0 +1,813 @@
+//! Charts embedded in ODS spreadsheets.
+//!
+//! Each chart is a nested ODF sub-document, such as `Object 1/content.xml`,
+//! referenced from a `draw:frame`/`draw:object` pair on the sheet. The
+//! `chart:chart` element of the sub-document is mapped onto the
+//! [`crate::ooxml::charts`] model, so charts read from .ods and .xlsx files
+//! can be handled the same way.
+//!
+//! Cell range addresses are converted to Excel syntax (`Sheet1!$B$2:$B$4`)
+//! and the series data is read from the cells of the spreadsheet.
+
+use super::formula::{split_unquoted, to_excel_formula};
+use super::parser::OdsParser;
+use super::{CellValue, Sheet};
+use crate::common::xml::qualified_attr;
+use crate::common::{Error, Result, trace};
+use crate::odf::core::OwnedPackage;
+use crate::ooxml::charts::types::{
+    AxisPosition, BarDirection, BarGrouping, LegendPosition, RadarStyle, ScatterStyle,
+};
+use crate::ooxml::charts::{
+    Area3DTypeGroup, AreaTypeGroup, Axis, Bar3DTypeGroup, BarTypeGroup, BubbleTypeGroup,
+    CategoryAxis, Chart, DoughnutTypeGroup, Legend, Line3DTypeGroup, LineTypeGroup, NumericData,
+    Pie3DTypeGroup, PieTypeGroup, RadarTypeGroup, ScatterTypeGroup, Series, SeriesAxis,
+    StockTypeGroup, StringData, Surface3DTypeGroup, SurfaceTypeGroup, TitleText, TypeGroup,
+    ValueAxis,
+};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+
+/// Media type of chart sub-documents in the manifest
+const CHART_MEDIA_TYPE: &str = "application/vnd.oasis.opendocument.chart";
+
+/// Read the charts referenced from content.xml into the sheets holding them.
+///
+/// Objects that are not charts, or whose sub-document is missing, are
+/// skipped. A chart that cannot be parsed is skipped with a warning rather
+/// than failing the whole spreadsheet.
+pub(crate) fn load_charts(
+    package: &OwnedPackage,
+    xml_content: &str,
+    sheets: &mut [Sheet],
+) -> Result<()> {
+    let objects = chart_objects(xml_content)?;
+    if objects.is_empty() {
+        return Ok(());
+    }
+
+    let package = package.package()?;
+    for (sheet_index, object) in objects {
+        if sheet_index >= sheets.len() {
+            continue;
+        }
+        let media_type = package.manifest().get_media_type(&format!("{object}/"));
+        if media_type.is_some_and(|media_type| media_type != CHART_MEDIA_TYPE) {
+            continue;
+        }
+        let path = format!("{object}/content.xml");
+        if !package.has_file(&path) {
+            continue;
+        }
+
+        let bytes = package.get_file(&path)?;
+        let xml = String::from_utf8(bytes)
+            .map_err(|_| Error::InvalidFormat(format!("Invalid UTF-8 in {path}")))?;
+        match parse_chart(&xml, &path) {
+            Ok(Some(mut chart)) => {
+                fill_series_from_cells(&mut chart, sheets, sheet_index);
+                sheets[sheet_index].charts.push(chart);
+            },
+            Ok(None) => {},
+            Err(_err) => {
+                trace::warn!(part = %path, error = %_err, "skipping unparsable chart object");
+            },
+        }
+    }
+    Ok(())
+}
+
+/// Find the embedded objects drawn on each sheet of content.xml.
+///
+/// Returns `(sheet index, object directory)` pairs in document order, with
+/// the directory normalized from `./Object 1` to `Object 1`.
+fn chart_objects(xml_content: &str) -> Result<Vec<(usize, String)>> {
+    let mut reader = Reader::from_str(xml_content);
+    let mut buf = Vec::new();
+    let mut objects = Vec::new();
+    let mut sheet_count = 0;
+    let mut table_depth = 0usize;
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) if e.name().as_ref() == b"table:table" => {
+                if table_depth == 0 {
+                    sheet_count += 1;
+                }
+                table_depth += 1;
+            },
+            Ok(Event::Empty(ref e)) if table_depth == 0 && e.name().as_ref() == b"table:table" => {
+                sheet_count += 1;
+            },
+            Ok(Event::End(ref e)) if e.name().as_ref() == b"table:table" => {
+                table_depth = table_depth.saturating_sub(1);
+            },
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                if table_depth > 0 && e.name().as_ref() == b"draw:object" =>
+            {
+                if let Some(href) = qualified_attr(e, b"xlink:href") {
+                    let href = href.strip_prefix("./").unwrap_or(&href);
+                    let href = href.trim_end_matches('/');
+                    // Links to files outside the package are not embedded charts
+                    if !href.is_empty() && !href.contains(':') && !href.starts_with("../") {
+                        objects.push((sheet_count - 1, href.to_string()));
+                    }
+                }
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(Error::xml_at(
+                    Some("content.xml"),
+                    xml_content.as_bytes(),
+                    reader.error_position(),
+                    e,
+                ));
+            },
+            _ => {},
+        }
+        buf.clear();
+    }
+
+    Ok(objects)
+}
+
+/// Properties of a `style:chart-properties` element that shape the plot
+#[derive(Debug, Clone, Copy, Default)]
+struct PlotProperties {
+    /// Bars run horizontally
+    vertical: bool,
+    stacked: bool,
+    percentage: bool,
+    three_dimensional: bool,
+}
+
+/// A `chart:axis` element
+#[derive(Debug, Default)]
+struct OdfAxis {
+    dimension: String,
+    secondary: bool,
+    title: Option<String>,
+    major_grid: bool,
+    minor_grid: bool,
+    categories: Option<String>,
+}
+
+/// A `chart:series` element
+#[derive(Debug, Default)]
+struct OdfSeries {
+    class: Option<String>,
+    values: Option<String>,
+    label: Option<String>,
+    domains: Vec<String>,
+}
+
+/// Element whose paragraphs make up the title being read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TitleOwner {
+    Chart,
+    Axis,
+}
+
+/// Parse the content.xml of a chart object.
+///
+/// Returns `None` if the sub-document holds no `chart:chart` element.
+fn parse_chart(xml_content: &str, part: &str) -> Result<Option<Chart>> {
+    let mut reader = Reader::from_str(xml_content);
+    let mut buf = Vec::new();
+
+    let mut styles: HashMap<String, PlotProperties> = HashMap::new();
+    let mut current_style: Option<(String, PlotProperties)> = None;
+
+    let mut found = false;
+    let mut class = String::from("bar");
+    let mut title: Option<String> = None;
+    let mut legend = None;
+    let mut plot_style = None;
+    let mut axes: Vec<OdfAxis> = Vec::new();
+    let mut series: Vec<OdfSeries> = Vec::new();
+
+    let mut in_axis = false;
+    let mut title_owner: Option<TitleOwner> = None;
+    let mut paragraphs: Vec<String> = Vec::new();
+    // Depth of the local data table, whose text is not part of any title
+    let mut table_depth = 0usize;
+
+    loop {
+        let event = reader.read_event_into(&mut buf);
+        let is_start = matches!(event, Ok(Event::Start(_)));
+        match event {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                b"style:style" => {
+                    if is_start
+                        && qualified_attr(e, b"style:family").as_deref() == Some("chart")
+                        && let Some(name) = qualified_attr(e, b"style:name")
+                    {
+                        current_style = Some((name, PlotProperties::default()));
+                    }
+                },
+                b"style:chart-properties" => {
+                    if let Some((_, properties)) = &mut current_style {
+                        read_plot_properties(e, properties);
+                    }
+                },
+                b"chart:chart" => {
+                    found = true;
+                    if let Some(value) = qualified_attr(e, b"chart:class") {
+                        class = chart_class(&value).to_string();
+                    }
+                },
+                b"chart:title" if is_start && table_depth == 0 => {
+                    title_owner = Some(if in_axis {
+                        TitleOwner::Axis
+                    } else {
+                        TitleOwner::Chart
+                    });
+                    paragraphs.clear();
+                },
+                b"text:p" if title_owner.is_some() => paragraphs.push(String::new()),
+                b"chart:legend" => {
+                    let position = qualified_attr(e, b"chart:legend-position");
+                    legend = Some(Legend::new(legend_position(position.as_deref())));
+                },
+                b"chart:plot-area" => plot_style = qualified_attr(e, b"chart:style-name"),
+                b"chart:axis" => {
+                    axes.push(OdfAxis {
+                        dimension: qualified_attr(e, b"chart:dimension").unwrap_or_default(),
+                        secondary: qualified_attr(e, b"chart:name")
+                            .is_some_and(|name| name.starts_with("secondary")),
+                        ..Default::default()
+                    });
+                    in_axis = is_start;
+                },
+                b"chart:grid" => {
+                    if let Some(axis) = axes.last_mut().filter(|_| in_axis) {
+                        match qualified_attr(e, b"chart:class").as_deref() {
+                            Some("minor") => axis.minor_grid = true,
+                            _ => axis.major_grid = true,
+                        }
+                    }
+                },
+                b"chart:categories" => {
+                    if let Some(axis) = axes.last_mut().filter(|_| in_axis) {
+                        axis.categories = qualified_attr(e, b"table:cell-range-address");
+                    }
+                },
+                b"chart:series" => series.push(OdfSeries {
+                    class: qualified_attr(e, b"chart:class")
+                        .map(|value| chart_class(&value).into()),
+                    values: qualified_attr(e, b"chart:values-cell-range-address"),
+                    label: qualified_attr(e, b"chart:label-cell-address"),
+                    domains: Vec::new(),
+                }),
+                b"chart:domain" => {
+                    if let (Some(current), Some(address)) = (
+                        series.last_mut(),
+                        qualified_attr(e, b"table:cell-range-address"),
+                    ) {
+                        current.domains.push(address);
+                    }
+                },
+                b"table:table" if is_start => table_depth += 1,
+                _ => {},
+            },
+            Ok(Event::Text(ref t)) => {
+                if title_owner.is_some()
+                    && let Some(paragraph) = paragraphs.last_mut()
+                {
+                    paragraph.push_str(&t.decode().unwrap_or_default());
+                }
+            },
+            Ok(Event::GeneralRef(ref r)) => {
+                if title_owner.is_some()
+                    && let Some(paragraph) = paragraphs.last_mut()
+                {
+                    paragraph.push_str(&OdsParser::resolve_reference(r));
+                }
+            },
+            Ok(Event::End(ref e)) => match e.name().as_ref() {
+                b"style:style" => {
+                    if let Some((name, properties)) = current_style.take() {
+                        styles.insert(name, properties);
+                    }
+                },
+                b"chart:title" => {
+                    let text = paragraphs.join("\n");
+                    match title_owner.take() {
+                        Some(TitleOwner::Chart) => title = Some(text),
+                        Some(TitleOwner::Axis) => {
+                            if let Some(axis) = axes.last_mut() {
+                                axis.title = Some(text);
+                            }
+                        },
+                        None => {},
+                    }
+                },
+                b"chart:axis" => in_axis = false,
+                b"table:table" => table_depth = table_depth.saturating_sub(1),
+                _ => {},
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(Error::xml_at(
+                    Some(part),
+                    xml_content.as_bytes(),
+                    reader.error_position(),
+                    e,
+                ));
+            },
+            _ => {},
+        }
+        buf.clear();
+    }
+
+    if !found {
+        return Ok(None);
+    }
+
+    let properties = plot_style
+        .and_then(|name| styles.get(&name).copied())
+        .unwrap_or_default();
+    let categories = axes
+        .iter()
+        .find(|axis| axis.dimension == "x")
+        .and_then(|axis| axis.categories.as_deref());
+
+    let mut chart = Chart::new();
+    chart.title = title.map(TitleText::from_string);
+    chart.legend = legend;
+
+    // Series of another class than the chart, as in a bar chart with a
+    // line series, go into a type group of their own
+    let mut groups: Vec<(String, TypeGroup)> = Vec::new();
+    for (index, odf_series) in series.iter().enumerate() {
+        let series_class = odf_series.class.as_deref().unwrap_or(&class);
+        let converted = convert_series(index as u32, odf_series, series_class, categories);
+        match groups.iter_mut().find(|(name, _)| name == series_class) {
+            Some((_, group)) => group.common_mut().add_series(converted),
+            None => {
+                let mut group = type_group(series_class, properties);
+                group.common_mut().add_series(converted);
+                groups.push((series_class.to_string(), group));
+            },
+        }
+    }
+    if groups.is_empty() {
+        groups.push((class.clone(), type_group(&class, properties)));
+    }
+
+    if !matches!(class.as_str(), "circle" | "ring") {
+        let xy = matches!(class.as_str(), "scatter" | "bubble");
+        chart.plot_area.axes = convert_axes(&axes, xy, properties.vertical);
+    }
+    chart.plot_area.type_groups = groups.into_iter().map(|(_, group)| group).collect();
+
+    Ok(Some(chart))
+}
+
+/// Read the plot properties of a `style:chart-properties` element
+fn read_plot_properties(e: &BytesStart<'_>, properties: &mut PlotProperties) {
+    for attr in e.attributes().flatten() {
+        let enabled = attr.value.as_ref() == b"true";
+        match attr.key.as_ref() {
+            b"chart:vertical" => properties.vertical = enabled,
+            b"chart:stacked" => properties.stacked = enabled,
+            b"chart:percentage" => properties.percentage = enabled,
+            b"chart:three-dimensional" => properties.three_dimensional = enabled,
+            _ => {},
+        }
+    }
+}
+
+/// Strip the namespace prefix from a `chart:class` value such as `chart:bar`
+fn chart_class(value: &str) -> &str {
+    value.rsplit(':').next().unwrap_or(value)
+}
+
+/// Map a `chart:legend-position` value onto the OOXML legend positions
+fn legend_position(value: Option<&str>) -> LegendPosition {
+    match value {
+        Some("start") => LegendPosition::Left,
+        Some("top") => LegendPosition::Top,
+        Some("bottom") => LegendPosition::Bottom,
+        Some("top-end") => LegendPosition::TopRight,
+        _ => LegendPosition::Right,
+    }
+}
+
+/// Create the type group for a chart class
+fn type_group(class: &str, properties: PlotProperties) -> TypeGroup {
+    let grouping = |default| {
+        if properties.percentage {
+            BarGrouping::PercentStacked
+        } else if properties.stacked {
+            BarGrouping::Stacked
+        } else {
+            default
+        }
+    };
+    let three_d = properties.three_dimensional;
+
+    match class {
+        "line" if three_d => {
+            TypeGroup::Line3D(Line3DTypeGroup::new(grouping(BarGrouping::Standard)))
+        },
+        "line" => TypeGroup::Line(LineTypeGroup::new(grouping(BarGrouping::Standard))),
+        "area" if three_d => {
+            TypeGroup::Area3D(Area3DTypeGroup::new(grouping(BarGrouping::Standard)))
+        },
+        "area" => TypeGroup::Area(AreaTypeGroup::new(grouping(BarGrouping::Standard))),
+        "circle" if three_d => TypeGroup::Pie3D(Pie3DTypeGroup::new()),
+        "circle" => TypeGroup::Pie(PieTypeGroup::new()),
+        "ring" => TypeGroup::Doughnut(DoughnutTypeGroup::new()),
+        "scatter" => TypeGroup::Scatter(ScatterTypeGroup::new(ScatterStyle::LineMarker)),
+        "bubble" => TypeGroup::Bubble(BubbleTypeGroup::new()),
+        "radar" => TypeGroup::Radar(RadarTypeGroup::new(RadarStyle::Marker)),
+        "filled-radar" => TypeGroup::Radar(RadarTypeGroup::new(RadarStyle::Filled)),
+        "stock" => TypeGroup::Stock(StockTypeGroup::new()),
+        "surface" if three_d => TypeGroup::Surface3D(Surface3DTypeGroup::new()),
+        "surface" => TypeGroup::Surface(SurfaceTypeGroup::new()),
+        // Bar charts, and classes without an OOXML counterpart such as gantt
+        _ => {
+            let direction = if properties.vertical {
+                BarDirection::Bar
+            } else {
+                BarDirection::Column
+            };
+            if three_d {
+                TypeGroup::Bar3D(Bar3DTypeGroup::new(
+                    direction,
+                    grouping(BarGrouping::Clustered),
+                ))
+            } else {
+                TypeGroup::Bar(BarTypeGroup::new(
+                    direction,
+                    grouping(BarGrouping::Clustered),
+                ))
+            }
+        },
+    }
+}
+
+/// Convert a `chart:series` element, with its ranges in Excel syntax
+fn convert_series(
+    index: u32,
+    odf_series: &OdfSeries,
+    class: &str,
+    categories: Option<&str>,
+) -> Series {
+    let mut series = Series::new(index);
+    series.title = odf_series
+        .label
+        .as_deref()
+        .map(|label| TitleText::from_ref(excel_range(label)));
+    let numbers = |address: &String| NumericData::from_ref(excel_range(address));
+
+    match class {
+        // The values are the Y values and the first domain the X values
+        "scatter" => {
+            series.x_values = odf_series.domains.first().map(numbers);
+            series.y_values = odf_series.values.as_ref().map(numbers);
+        },
+        // The values are the bubble sizes, then come the Y and X domains
+        "bubble" => {
+            series.bubble_sizes = odf_series.values.as_ref().map(numbers);
+            series.y_values = odf_series.domains.first().map(numbers);
+            series.x_values = odf_series.domains.get(1).map(numbers);
+        },
+        _ => {
+            series.values = odf_series.values.as_ref().map(numbers);
+            series.categories =
+                categories.map(|address| StringData::from_ref(excel_range(address)));
+        },
+    }
+    series
+}
+
+/// Convert the `chart:axis` elements
+///
+/// Scatter and bubble charts have a value axis for X; other charts have a
+/// category axis. Horizontal bar charts swap the axis positions.
+fn convert_axes(axes: &[OdfAxis], xy: bool, vertical: bool) -> Vec<Axis> {
+    const X_ID: u32 = 1;
+    const Y_ID: u32 = 2;
+    const Z_ID: u32 = 3;
+
+    let mut converted = Vec::new();
+    for axis in axes {
+        let (id, cross_id) = match axis.dimension.as_str() {
+            "x" => (X_ID, Y_ID),
+            "y" => (Y_ID, X_ID),
+            "z" => (Z_ID, Y_ID),
+            _ => continue,
+        };
+        // Secondary axes get an id of their own on the opposite side
+        let id = if axis.secondary { id + Z_ID } else { id };
+        let position = match (axis.dimension.as_str(), vertical, axis.secondary) {
+            ("x", false, false) | ("y", true, false) | ("z", _, _) => AxisPosition::Bottom,
+            ("x", true, false) | ("y", false, false) => AxisPosition::Left,
+            ("x", false, true) | ("y", true, true) => AxisPosition::Top,
+            _ => AxisPosition::Right,
+        };
+
+        let mut converted_axis = match axis.dimension.as_str() {
+            "x" if !xy => Axis::Category(CategoryAxis::new(id, position, cross_id)),
+            "z" => Axis::Series(SeriesAxis::new(id, position, cross_id)),
+            _ => Axis::Value(ValueAxis::new(id, position, cross_id)),
+        };
+        let common = converted_axis.common_mut();
+        common.title = axis.title.clone().map(TitleText::from_string);
+        common.show_major_gridlines = axis.major_grid;
+        common.show_minor_gridlines = axis.minor_grid;
+        converted.push(converted_axis);
+    }
+    converted
+}
+
+/// Convert an ODF cell range address list, such as `Sheet1.$B$2:.$B$4`,
+/// to Excel syntax
+///
+/// Several ranges are joined into a parenthesized union. Addresses that
+/// cannot be converted are kept as they are.
+fn excel_range(address: &str) -> String {
+    let ranges: Vec<String> = split_unquoted(address.trim(), ' ')
+        .into_iter()
+        .filter(|range| !range.is_empty())
+        .map(|range| to_excel_formula(&format!("[{range}]")).unwrap_or_else(|| range.into()))
+        .collect();
+
+    match ranges.as_slice() {
+        [] => address.to_string(),
+        [single] => single.clone(),
+        _ => format!("({})", ranges.join(",")),
+    }
+}
+
+/// Fill in the series titles and data from the cells of the spreadsheet
+fn fill_series_from_cells(chart: &mut Chart, sheets: &[Sheet], sheet_index: usize) {
+    for series in chart.plot_area.series_mut() {
+        if let Some(TitleText::Reference(source)) = &series.title
+            && let Some(cells) = range_values(sheets, sheet_index, &source.formula)
+        {
+            let text = cells.first().copied().map(cell_text).unwrap_or_default();
+            series.title = Some(TitleText::from_string(text));
+        }
+
+        if let Some(categories) = series.categories.as_mut()
+            && let Some(source) = &categories.source_ref
+            && let Some(cells) = range_values(sheets, sheet_index, &source.formula)
+        {
+            categories.values = cells.iter().copied().map(cell_text).collect();
+        }
+
+        for data in [
+            series.values.as_mut(),
+            series.x_values.as_mut(),
+            series.y_values.as_mut(),
+            series.bubble_sizes.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if let Some(source) = &data.source_ref
+                && let Some(cells) = range_values(sheets, sheet_index, &source.formula)
+            {
+                data.values = cells.iter().copied().map(cell_number).collect();
+            }
+        }
+    }
+}
+
+/// Read the cells of a range such as `Sheet1!$B$2:$B$4`, row by row
+///
+/// Unqualified ranges are resolved against the sheet holding the chart.
+/// Returns `None` for unions and references to unknown sheets.
+fn range_values<'a>(
+    sheets: &'a [Sheet],
+    sheet_index: usize,
+    formula: &str,
+) -> Option<Vec<Option<&'a CellValue>>> {
+    let (sheet_name, cells) = match formula.rsplit_once('!') {
+        Some((sheet, cells)) => {
+            let sheet = match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                Some(quoted) => quoted.replace("''", "'"),
+                None => sheet.to_string(),
+            };
+            (Some(sheet), cells)
+        },
+        None => (None, formula),
+    };
+    let sheet = match sheet_name {
+        Some(name) => sheets.iter().find(|sheet| sheet.name == name)?,
+        None => sheets.get(sheet_index)?,
+    };
+
+    let (first, last) = cells.split_once(':').unwrap_or((cells, cells));
+    let (first_row, first_col) = cell_position(first)?;
+    let (last_row, last_col) = cell_position(last)?;
+
+    let mut values = Vec::new();
+    for row in first_row.min(last_row)..=first_row.max(last_row) {
+        for col in first_col.min(last_col)..=first_col.max(last_col) {
+            values.push(
+                sheet
+                    .rows
+                    .get(row)
+                    .and_then(|row| row.cells.get(col))
+                    .map(|cell| &cell.value),
+            );
+        }
+    }
+    Some(values)
+}
+
+/// Parse a cell address such as `$B$2` into a 0-based `(row, col)`
+fn cell_position(cell: &str) -> Option<(usize, usize)> {
+    let cell = cell.replace('$', "");
+    let digits = cell.find(|c: char| c.is_ascii_digit())?;
+    let (letters, number) = cell.split_at(digits);
+    if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let col = letters.chars().try_fold(0usize, |col, c| {
+        Some(col * 26 + (c.to_ascii_uppercase() as usize - 'A' as usize + 1))
+    })?;
+    let row: usize = number.parse().ok()?;
+    Some((row.checked_sub(1)?, col - 1))
+}
+
+/// Display text of a cell used as a chart label
+fn cell_text(value: Option<&CellValue>) -> String {
+    match value {
+        None | Some(CellValue::Empty) => String::new(),
+        Some(CellValue::Text(s) | CellValue::Date(s) | CellValue::Time(s)) => s.clone(),
+        Some(CellValue::Number(n) | CellValue::Currency(n, _) | CellValue::Percentage(n)) => {
+            n.to_string()
+        },
+        Some(CellValue::Boolean(b)) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+    }
+}
+
+/// Numeric value of a cell used as a chart data point; non-numeric cells are `NaN`
+fn cell_number(value: Option<&CellValue>) -> f64 {
+    match value {
+        Some(CellValue::Number(n) | CellValue::Currency(n, _) | CellValue::Percentage(n)) => *n,
+        Some(CellValue::Boolean(b)) => f64::from(u8::from(*b)),
+        Some(CellValue::Text(s)) => s.trim().parse().unwrap_or(f64::NAN),
+        _ => f64::NAN,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::odf::Spreadsheet;
+    use crate::odf::core::PackageWriter;
+    use crate::ooxml::charts::types::AxisPosition;
+
+    const NS: &str = r#"xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:style="urn:oasis:names:tc:opendocument:xmlns:style:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:draw="urn:oasis:names:tc:opendocument:xmlns:drawing:1.0" xmlns:chart="urn:oasis:names:tc:opendocument:xmlns:chart:1.0" xmlns:xlink="http://www.w3.org/1999/xlink""#;
+
+    fn bar_chart_xml() -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content {NS}><office:automatic-styles><style:style style:name="ch1" style:family="chart"><style:chart-properties chart:vertical="true" chart:stacked="true"/></style:style></office:automatic-styles><office:body><office:chart><chart:chart chart:class="chart:bar"><chart:title><text:p>Sales &amp; costs</text:p></chart:title><chart:legend chart:legend-position="bottom"/><chart:plot-area chart:style-name="ch1" table:cell-range-address="Data.$A$1:.$C$4"><chart:axis chart:dimension="x" chart:name="primary-x"><chart:title><text:p>Month</text:p></chart:title><chart:categories table:cell-range-address="Data.$A$2:.$A$4"/></chart:axis><chart:axis chart:dimension="y" chart:name="primary-y"><chart:title><text:p>Amount</text:p></chart:title><chart:grid chart:class="major"/></chart:axis><chart:series chart:values-cell-range-address="Data.$B$2:.$B$4" chart:label-cell-address="Data.$B$1"/><chart:series chart:class="chart:line" chart:values-cell-range-address="Data.$C$2:.$C$4" chart:label-cell-address="Data.$C$1"/></chart:plot-area><table:table table:name="local-table"><table:table-rows><table:table-row><table:table-cell><text:p>Cached</text:p></table:table-cell></table:table-row></table:table-rows></table:table></chart:chart></office:chart></office:body></office:document-content>"#
+        )
+    }
+
+    fn spreadsheet_with_chart() -> Vec<u8> {
+        let rows = [
+            ["Month", "Sales", "Costs"],
+            ["Jan", "10", "4"],
+            ["Feb", "20", "8"],
+            ["Mar", "30", "12"],
+        ]
+        .iter()
+        .enumerate()
+        .map(|(index, row)| {
+            let cells: String = row
+                .iter()
+                .map(|value| match value.parse::<f64>() {
+                    Ok(number) if index > 0 => format!(
+                        r#"<table:table-cell office:value-type="float" office:value="{number}"><text:p>{value}</text:p></table:table-cell>"#
+                    ),
+                    _ => format!(
+                        r#"<table:table-cell office:value-type="string"><text:p>{value}</text:p></table:table-cell>"#
+                    ),
+                })
+                .collect();
+            format!("<table:table-row>{cells}</table:table-row>")
+        })
+        .collect::<String>();
+        let content = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content {NS}><office:body><office:spreadsheet><table:table table:name="Summary"><table:table-row><table:table-cell/></table:table-row></table:table><table:table table:name="Data"><table:shapes><draw:frame draw:name="Chart 1"><draw:object draw:notify-on-update-of-ranges="Data.A1:Data.C4" xlink:href="./Object 1" xlink:type="simple"/><draw:image xlink:href="./ObjectReplacements/Object 1"/></draw:frame></table:shapes>{rows}</table:table></office:spreadsheet></office:body></office:document-content>"#
+        );
+
+        let mut writer = PackageWriter::new();
+        writer
+            .set_mimetype("application/vnd.oasis.opendocument.spreadsheet")
+            .unwrap();
+        writer.add_file("content.xml", content.as_bytes()).unwrap();
+        writer
+            .add_file("Object 1/content.xml", bar_chart_xml().as_bytes())
+            .unwrap();
+        writer.finish_to_bytes().unwrap()
+    }
+
+    #[test]
+    fn test_sheet_charts() {
+        let mut spreadsheet = Spreadsheet::from_bytes(spreadsheet_with_chart()).unwrap();
+        let sheets = spreadsheet.sheets().unwrap();
+        assert!(sheets[0].charts().unwrap().is_empty());
+
+        let charts = sheets[1].charts().unwrap();
+        assert_eq!(charts.len(), 1);
+        let chart = &charts[0];
+        assert!(
+            matches!(&chart.title, Some(TitleText::Literal(text)) if text.text == "Sales & costs")
+        );
+        assert_eq!(
+            chart.legend.as_ref().map(|legend| legend.position),
+            Some(LegendPosition::Bottom)
+        );
+
+        let groups = &chart.plot_area.type_groups;
+        assert_eq!(groups.len(), 2);
+        let TypeGroup::Bar(bar) = &groups[0] else {
+            panic!("expected a bar group, got {:?}", groups[0]);
+        };
+        assert_eq!(bar.direction, BarDirection::Bar);
+        assert_eq!(bar.grouping, BarGrouping::Stacked);
+        assert!(matches!(groups[1], TypeGroup::Line(_)));
+
+        let series: Vec<_> = chart.plot_area.series().collect();
+        assert!(matches!(&series[0].title, Some(TitleText::Literal(text)) if text.text == "Sales"));
+        let values = series[0].values.as_ref().unwrap();
+        assert_eq!(
+            values.source_ref.as_ref().unwrap().formula,
+            "Data!$B$2:$B$4"
+        );
+        assert_eq!(values.values, [10.0, 20.0, 30.0]);
+        let categories = series[1].categories.as_ref().unwrap();
+        assert_eq!(categories.values, ["Jan", "Feb", "Mar"]);
+        assert_eq!(series[1].values.as_ref().unwrap().values, [4.0, 8.0, 12.0]);
+
+        let axes = &chart.plot_area.axes;
+        assert_eq!(axes.len(), 2);
+        let Axis::Category(x_axis) = &axes[0] else {
+            panic!("expected a category axis");
+        };
+        assert_eq!(x_axis.common.position, AxisPosition::Left);
+        assert!(
+            matches!(&x_axis.common.title, Some(TitleText::Literal(text)) if text.text == "Month")
+        );
+        assert!(axes[1].common().show_major_gridlines);
+    }
+
+    #[test]
+    fn test_parse_scatter_and_pie() {
+        let scatter = format!(
+            r#"<office:document-content {NS}><office:body><office:chart><chart:chart chart:class="chart:scatter"><chart:plot-area><chart:axis chart:dimension="x"/><chart:axis chart:dimension="y"/><chart:series chart:values-cell-range-address="'My Sheet'.B1:'My Sheet'.B3"><chart:domain table:cell-range-address="'My Sheet'.A1:'My Sheet'.A3"/></chart:series></chart:plot-area></chart:chart></office:chart></office:body></office:document-content>"#
+        );
+        let chart = parse_chart(&scatter, "Object 1/content.xml")
+            .unwrap()
+            .unwrap();
+        assert!(matches!(chart.plot_area.axes[0], Axis::Value(_)));
+        let series = chart.plot_area.series().next().unwrap();
+        let x_values = series
+            .x_values
+            .as_ref()
+            .unwrap()
+            .source_ref
+            .as_ref()
+            .unwrap();
+        assert_eq!(x_values.formula, "'My Sheet'!A1:A3");
+        assert!(series.y_values.is_some());
+
+        let pie = format!(
+            r#"<office:document-content {NS}><office:body><office:chart><chart:chart chart:class="chart:circle"><chart:plot-area><chart:series chart:values-cell-range-address="Sheet1.B1:Sheet1.B3"/></chart:plot-area></chart:chart></office:chart></office:body></office:document-content>"#
+        );
+        let chart = parse_chart(&pie, "Object 2/content.xml").unwrap().unwrap();
+        assert!(matches!(chart.plot_area.type_groups[0], TypeGroup::Pie(_)));
+        assert!(chart.plot_area.axes.is_empty());
+
+        let formula = format!(
+            r#"<office:document-content {NS}><office:body><office:formula/></office:body></office:document-content>"#
+        );
+        assert!(
+            parse_chart(&formula, "Object 3/content.xml")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_excel_range() {
+        assert_eq!(excel_range("Sheet1.$A$1:.$A$3"), "Sheet1!$A$1:$A$3");
+        assert_eq!(
+            excel_range("Sheet1.A1:Sheet1.A3 Sheet1.C1:Sheet1.C3"),
+            "(Sheet1!A1:A3,Sheet1!C1:C3)"
+        );
+        assert_eq!(cell_position("$AB$10"), Some((9, 27)));
+        assert_eq!(cell_position("A0"), None);
+    }
+}
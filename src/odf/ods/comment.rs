@@ -0,0 +1,70 @@
+//! Cell comments for ODS spreadsheets.
+//!
+//! Comments are stored as an `office:annotation` element at the start of the
+//! commented `table:table-cell`, with optional `dc:creator` and `dc:date`
+//! children followed by the comment paragraphs.
+
+use crate::common::xml::escape_xml;
+
+/// A comment attached to a cell.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CellComment {
+    /// Row index (0-based)
+    pub row: usize,
+    /// Column index (0-based)
+    pub col: usize,
+    /// Comment text; paragraphs are separated by newlines
+    pub text: String,
+    /// Author of the comment
+    pub author: Option<String>,
+    /// Creation time as an ISO 8601 date-time
+    pub date: Option<String>,
+}
+
+impl CellComment {
+    /// Create a comment on the cell at `row`, `col` (0-based).
+    pub fn new(row: usize, col: usize, text: &str) -> Self {
+        Self {
+            row,
+            col,
+            text: text.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Get the `office:annotation` element of the comment.
+    pub(super) fn to_xml(&self) -> String {
+        let mut out = String::from("<office:annotation>");
+        if let Some(author) = &self.author {
+            out.push_str(&format!("<dc:creator>{}</dc:creator>", escape_xml(author)));
+        }
+        if let Some(date) = &self.date {
+            out.push_str(&format!("<dc:date>{}</dc:date>", escape_xml(date)));
+        }
+        for paragraph in self.text.split('\n') {
+            out.push_str(&format!("<text:p>{}</text:p>", escape_xml(paragraph)));
+        }
+        out.push_str("</office:annotation>");
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_comment_xml() {
+        let comment = CellComment {
+            author: Some("Ann & Bob".to_string()),
+            date: Some("2024-03-15T10:00:00".to_string()),
+            ..CellComment::new(1, 2, "First\nSecond")
+        };
+        assert_eq!(
+            comment.to_xml(),
+            "<office:annotation><dc:creator>Ann &amp; Bob</dc:creator>\
+             <dc:date>2024-03-15T10:00:00</dc:date><text:p>First</text:p>\
+             <text:p>Second</text:p></office:annotation>"
+        );
+    }
+}
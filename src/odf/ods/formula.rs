@@ -515,6 +515,303 @@ pub fn extract_functions(formula: &Formula) -> SmallVec<[&str; 4]> {
         .collect()
 }
 
+// ============================================================================
+// TRANSLATION TO EXCEL SYNTAX
+// ============================================================================
+
+/// OpenFormula functions whose Excel name differs beyond the
+/// `COM.MICROSOFT.` prefix
+static FUNCTION_RENAMES: phf::Map<&'static str, &'static str> = phf::phf_map! {
+    "LEGACY.CHIDIST" => "CHIDIST",
+    "LEGACY.CHIINV" => "CHIINV",
+    "LEGACY.FDIST" => "FDIST",
+    "LEGACY.FINV" => "FINV",
+    "LEGACY.NORMSDIST" => "NORMSDIST",
+    "LEGACY.NORMSINV" => "NORMSINV",
+    "LEGACY.TDIST" => "TDIST",
+    "LEGACY.TINV" => "TINV",
+    "ORG.LIBREOFFICE.REGEX" => "REGEX",
+};
+
+/// Prefix of Excel-only functions in OpenFormula
+const MICROSOFT_PREFIX: &str = "COM.MICROSOFT.";
+
+/// Error literals shared by OpenFormula and Excel
+const ERROR_LITERALS: [&str; 7] = [
+    "#NULL!", "#DIV/0!", "#VALUE!", "#REF!", "#NAME?", "#NUM!", "#N/A",
+];
+
+/// Translate an OpenFormula expression into Excel A1 syntax.
+///
+/// The namespace prefix (`of:`, `oooc:`) and the leading `=` are removed, so
+/// the result matches the formula text stored in
+/// [`crate::sheet::CellValue::Formula`]:
+///
+/// - References: `[.B2]` becomes `B2`, `[Sheet2.A1:.A10]` becomes
+///   `Sheet2!A1:A10` and `[$'My Sheet'.$C$3]` becomes `'My Sheet'!$C$3`
+/// - Argument separators: `;` becomes `,`
+/// - Functions: the `COM.MICROSOFT.` prefix and `LEGACY.` names are mapped
+///   back to their Excel names, LibreOffice's `ORG.LIBREOFFICE.REGEX`
+///   becomes `REGEX`, and `TRUE()`/`FALSE()` become literals
+///
+/// Formulas in the `msoxl:` namespace are already Excel syntax and are only
+/// stripped of their prefix.
+///
+/// Returns `None` for constructs Excel cannot express, such as references
+/// to external files, 3D ranges spanning several sheets, and the reference
+/// intersection (`!`) and union (`~`) operators.
+///
+/// # Examples
+///
+/// ```
+/// use litchi::odf::to_excel_formula;
+///
+/// assert_eq!(
+///     to_excel_formula("of:=SUM([.A1:.A3];[Sheet2.B1])").as_deref(),
+///     Some("SUM(A1:A3,Sheet2!B1)")
+/// );
+/// ```
+pub fn to_excel_formula(formula: &str) -> Option<String> {
+    let mut body = formula.trim();
+
+    if let Some((namespace, rest)) = body.split_once(':')
+        && !namespace.is_empty()
+        && namespace.chars().all(|c| c.is_ascii_lowercase())
+        && rest.trim_start().starts_with('=')
+    {
+        if namespace == "msoxl" {
+            return Some(rest.trim_start()[1..].trim().to_string());
+        }
+        body = rest.trim_start();
+    }
+    let body = body.strip_prefix('=').unwrap_or(body);
+
+    let chars: Vec<char> = body.chars().collect();
+    let mut out = String::with_capacity(body.len());
+    let mut in_array = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                // String literal with "" escapes, copied verbatim
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    if chars[i] == '"' {
+                        if chars.get(i + 1) == Some(&'"') {
+                            out.push('"');
+                            i += 1;
+                        } else {
+                            break;
+                        }
+                    }
+                    i += 1;
+                }
+                i += 1;
+            },
+            '[' => {
+                let end = find_reference_end(&chars, i + 1)?;
+                let mut inner: String = chars[i + 1..end].iter().collect();
+                i = end + 1;
+
+                // Older files write ranges as [.A1]:[.B2]
+                if chars.get(i) == Some(&':') && chars.get(i + 1) == Some(&'[') {
+                    let end = find_reference_end(&chars, i + 2)?;
+                    inner.push(':');
+                    inner.extend(&chars[i + 2..end]);
+                    i = end + 1;
+                }
+
+                out.push_str(&translate_reference(&inner)?);
+            },
+            '#' => {
+                let rest: String = chars[i..].iter().collect();
+                let literal = ERROR_LITERALS
+                    .iter()
+                    .find(|literal| rest.to_ascii_uppercase().starts_with(*literal))?;
+                out.push_str(literal);
+                i += literal.chars().count();
+            },
+            '{' | '}' => {
+                in_array = c == '{';
+                out.push(c);
+                i += 1;
+            },
+            ';' => {
+                out.push(',');
+                i += 1;
+            },
+            '|' if in_array => {
+                out.push(';');
+                i += 1;
+            },
+            '!' | '~' => return None,
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_ascii_alphanumeric() || matches!(chars[i], '_' | '.'))
+                {
+                    i += 1;
+                }
+                let name: String = chars[start..i].iter().collect();
+
+                let mut next = i;
+                while next < chars.len() && chars[next].is_whitespace() {
+                    next += 1;
+                }
+                if chars.get(next) != Some(&'(') {
+                    // Named expression or boolean literal
+                    out.push_str(&name);
+                    continue;
+                }
+
+                let upper = name.to_ascii_uppercase();
+                let after_paren = (next + 1..chars.len()).find(|&j| !chars[j].is_whitespace());
+                if matches!(upper.as_str(), "TRUE" | "FALSE")
+                    && let Some(close) = after_paren
+                    && chars[close] == ')'
+                {
+                    out.push_str(&upper);
+                    i = close + 1;
+                    continue;
+                }
+
+                let excel_name = upper.strip_prefix(MICROSOFT_PREFIX).unwrap_or(&upper);
+                out.push_str(
+                    FUNCTION_RENAMES
+                        .get(excel_name)
+                        .copied()
+                        .unwrap_or(excel_name),
+                );
+            },
+            _ => {
+                out.push(c);
+                i += 1;
+            },
+        }
+    }
+
+    Some(out)
+}
+
+/// Translate an OpenFormula expression into the evaluation engine's AST.
+///
+/// Unqualified references resolve against `current_sheet`. Returns `None`
+/// if the formula cannot be translated or parsed.
+#[cfg(feature = "eval_engine")]
+pub fn to_expr(formula: &str, current_sheet: &str) -> Option<crate::sheet::eval::parser::Expr> {
+    crate::sheet::eval::parser::parse_expression(current_sheet, &to_excel_formula(formula)?)
+}
+
+/// Find the `]` closing a reference, skipping quoted sheet names
+fn find_reference_end(chars: &[char], start: usize) -> Option<usize> {
+    let mut in_quote = false;
+    for (offset, &c) in chars[start..].iter().enumerate() {
+        match c {
+            '\'' => in_quote = !in_quote,
+            ']' if !in_quote => return Some(start + offset),
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Translate the content of a bracketed reference, such as `.A1:.B2` or
+/// `$'My Sheet'.$A$1`
+fn translate_reference(inner: &str) -> Option<String> {
+    // References into other documents: ['file:///data.ods'#$Sheet1.A1]
+    if inner.contains('#') && !inner.contains("#REF!") {
+        return None;
+    }
+
+    let mut parts = split_unquoted(inner, ':').into_iter();
+    let (start_sheet, start_cell) = split_sheet(parts.next()?)?;
+    let end = match parts.next() {
+        Some(part) => Some(split_sheet(part)?),
+        None => None,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+
+    if start_cell.contains('#') || end.as_ref().is_some_and(|(_, cell)| cell.contains('#')) {
+        return Some("#REF!".to_string());
+    }
+
+    let mut out = String::new();
+    if let Some(sheet) = &start_sheet {
+        out.push_str(&quote_sheet_name(sheet));
+        out.push('!');
+    }
+    out.push_str(start_cell);
+
+    if let Some((end_sheet, end_cell)) = end {
+        // A range spanning sheets is a 3D reference
+        if end_sheet.is_some() && end_sheet != start_sheet {
+            return None;
+        }
+        out.push(':');
+        out.push_str(end_cell);
+    }
+
+    Some(out)
+}
+
+/// Split at a separator that is not inside a quoted sheet name
+pub(super) fn split_unquoted(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut in_quote = false;
+    let mut start = 0;
+
+    for (index, c) in text.char_indices() {
+        if c == '\'' {
+            in_quote = !in_quote;
+        } else if c == separator && !in_quote {
+            parts.push(&text[start..index]);
+            start = index + c.len_utf8();
+        }
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+/// Split an address such as `$Sheet1.$A$1` into its sheet name and cell
+fn split_sheet(address: &str) -> Option<(Option<String>, &str)> {
+    let address = address.trim();
+    let cell = *split_unquoted(address, '.').last()?;
+
+    let sheet = address[..address.len() - cell.len()].trim_end_matches('.');
+    let sheet = sheet.strip_prefix('$').unwrap_or(sheet);
+    if sheet.is_empty() {
+        return Some((None, cell));
+    }
+
+    let name = match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+        Some(quoted) => quoted.replace("''", "'"),
+        None => sheet.to_string(),
+    };
+    Some((Some(name), cell))
+}
+
+/// Quote a sheet name for Excel if it contains anything but letters,
+/// digits and underscores
+fn quote_sheet_name(name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+    if plain {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\'', "''"))
+    }
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -869,4 +1166,71 @@ mod tests {
         assert!(funcs.contains(&"SUM"));
         assert!(funcs.contains(&"AVERAGE"));
     }
+
+    #[test]
+    fn test_to_excel_references() {
+        assert_eq!(to_excel_formula("of:=[.B2]").as_deref(), Some("B2"));
+        assert_eq!(
+            to_excel_formula("of:=SUM([Sheet2.A1:.A10])").as_deref(),
+            Some("SUM(Sheet2!A1:A10)")
+        );
+        assert_eq!(
+            to_excel_formula("of:=[$'My Sheet'.$A$1]+[.A1]:[.B2]").as_deref(),
+            Some("'My Sheet'!$A$1+A1:B2")
+        );
+        assert_eq!(
+            to_excel_formula("=[$'It''s'.C3]").as_deref(),
+            Some("'It''s'!C3")
+        );
+        assert_eq!(to_excel_formula("of:=[.#REF!]").as_deref(), Some("#REF!"));
+    }
+
+    #[test]
+    fn test_to_excel_functions_and_separators() {
+        assert_eq!(
+            to_excel_formula("of:=IF([.A1]>0;\"a;b\";TRUE())").as_deref(),
+            Some("IF(A1>0,\"a;b\",TRUE)")
+        );
+        assert_eq!(
+            to_excel_formula("of:=com.microsoft.ifs([.A1]=1;2)").as_deref(),
+            Some("IFS(A1=1,2)")
+        );
+        assert_eq!(
+            to_excel_formula("of:=ORG.LIBREOFFICE.REGEX([.A1];\"\\d+\")").as_deref(),
+            Some("REGEX(A1,\"\\d+\")")
+        );
+        assert_eq!(
+            to_excel_formula("of:=SUM({1;2|3;4})").as_deref(),
+            Some("SUM({1,2;3,4})")
+        );
+        assert_eq!(
+            to_excel_formula("msoxl:=SUM(A1:A3)").as_deref(),
+            Some("SUM(A1:A3)")
+        );
+    }
+
+    #[test]
+    fn test_to_excel_unsupported() {
+        assert_eq!(to_excel_formula("of:=['file:///a.ods'#$Sheet1.A1]"), None);
+        assert_eq!(to_excel_formula("of:=SUM([$Sheet1.A1:$Sheet3.A1])"), None);
+        assert_eq!(to_excel_formula("of:=SUM([.A1:.B2]![.B1:.C2])"), None);
+    }
+
+    #[cfg(feature = "eval_engine")]
+    #[test]
+    fn test_to_expr() {
+        use crate::sheet::eval::parser::Expr;
+
+        match to_expr("of:=[Sheet2.B3]", "Sheet1") {
+            Some(Expr::Reference { sheet, row, col }) => {
+                assert_eq!(sheet, "Sheet2");
+                assert_eq!((row, col), (3, 2));
+            },
+            other => panic!("Expected reference, got {:?}", other),
+        }
+        assert!(matches!(
+            to_expr("of:=SUM([.A1:.A3])", "Sheet1"),
+            Some(Expr::FunctionCall { .. })
+        ));
+    }
 }
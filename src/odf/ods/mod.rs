@@ -37,14 +37,21 @@
 //! - ✅ `save()` / `to_bytes()` - Write to file or bytes
 //! - ✅ `MutableSpreadsheet` - Modify existing spreadsheets
 //!
+//! ## ✅ Annotations and Protection (`named_range.rs`, `comment.rs`, `protection.rs`)
+//! - ✅ Named ranges (`table:named-expressions`)
+//! - ✅ Cell comments with author and date (`office:annotation`)
+//! - ✅ Sheet protection with the legacy SHA-1 password key
+//!
+//! ## ✅ Charts (`chart.rs`, requires the `ooxml` feature) - READ ONLY
+//! - ✅ `Sheet::charts()` - Charts embedded as `Object N` sub-documents
+//! - ✅ Chart class, title, legend, axis titles, series and category ranges
+//! - ✅ Mapped onto the `ooxml::charts` model shared with .xlsx files
+//!
 //! ## 🚧 TODO - Advanced Features
-//! - ⚠️ Chart creation and parsing (embedded charts)
+//! - ⚠️ Chart creation (embedded charts)
 //! - ⚠️ Data validation rules
 //! - ⚠️ Conditional formatting
 //! - ⚠️ Pivot tables
-//! - ⚠️ Named ranges (cell range naming)
-//! - ⚠️ Cell comments/notes
-//! - ⚠️ Sheet protection and locking
 //! - ⚠️ Filter and sort criteria
 //! - ⚠️ Sparklines
 //! - ⚠️ Data tables and scenarios
@@ -58,21 +65,33 @@
 
 mod builder;
 mod cell;
+#[cfg(feature = "ooxml")]
+mod chart;
+mod comment;
 /// OpenFormula parsing and support
 pub mod formula;
 mod mutable;
+mod named_range;
 mod parser;
+mod protection;
 mod row;
 mod sheet;
 mod spreadsheet;
+mod workbook;
 
 pub use builder::SpreadsheetBuilder;
 pub use cell::{Cell, CellValue};
+pub use comment::CellComment;
 pub use mutable::MutableSpreadsheet;
+pub use named_range::NamedRange;
+pub use protection::SheetProtection;
 pub use row::Row;
 pub use sheet::Sheet;
 pub use spreadsheet::Spreadsheet;
 
 // Re-export formula types for public API
+pub use formula::to_excel_formula;
+#[cfg(feature = "eval_engine")]
+pub use formula::to_expr;
 #[allow(unused_imports)] // Public API exports
 pub use formula::{CellRef, Formula, RangeRef, Token};
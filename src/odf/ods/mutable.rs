@@ -3,9 +3,12 @@
 //! This module provides a mutable wrapper around ODS spreadsheets that allows
 //! for in-place modification of sheets, rows, and cells.
 
-use crate::common::{Metadata, Result, xml::escape_xml};
-use crate::odf::core::{OdfStructure, PackageWriter};
-use crate::odf::ods::{Cell, CellValue, Row, Sheet, Spreadsheet};
+use super::named_range::named_expressions_xml;
+use crate::common::{CustomPropertyValue, Metadata, Result, xml::escape_xml};
+use crate::odf::core::{OdfStructure, PackageWriter, meta_xml};
+use crate::odf::ods::{
+    Cell, CellComment, CellValue, NamedRange, Row, Sheet, SheetProtection, Spreadsheet,
+};
 use std::path::Path;
 
 /// A mutable ODS spreadsheet that supports in-place modifications.
@@ -28,6 +31,8 @@ use std::path::Path;
 pub struct MutableSpreadsheet {
     /// Mutable sheets
     sheets: Vec<Sheet>,
+    /// Named ranges of the spreadsheet
+    named_ranges: Vec<NamedRange>,
     /// Document metadata
     metadata: Metadata,
     /// Original MIME type
@@ -60,7 +65,8 @@ impl MutableSpreadsheet {
         }
     }
 
-    fn push_cell(out: &mut String, cell: &Cell) {
+    fn push_cell(out: &mut String, cell: &Cell, comment: Option<&CellComment>) {
+        let annotation = comment.map(CellComment::to_xml).unwrap_or_default();
         let formula_attr = cell
             .formula
             .as_deref()
@@ -70,14 +76,14 @@ impl MutableSpreadsheet {
         match &cell.value {
             CellValue::Text(_) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="string"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="string">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     escape_xml(&cell.text)
                 ));
             },
             CellValue::Number(f) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="float" office:value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="float" office:value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     f,
                     escape_xml(&cell.text)
@@ -85,7 +91,7 @@ impl MutableSpreadsheet {
             },
             CellValue::Currency(f, currency) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="currency" office:value="{}" office:currency="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="currency" office:value="{}" office:currency="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     f,
                     escape_xml(currency),
@@ -94,7 +100,7 @@ impl MutableSpreadsheet {
             },
             CellValue::Percentage(f) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="percentage" office:value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="percentage" office:value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     f,
                     escape_xml(&cell.text)
@@ -102,7 +108,7 @@ impl MutableSpreadsheet {
             },
             CellValue::Date(d) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="date" office:date-value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="date" office:date-value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     escape_xml(d),
                     escape_xml(&cell.text)
@@ -110,7 +116,7 @@ impl MutableSpreadsheet {
             },
             CellValue::Time(t) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="time" office:time-value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="time" office:time-value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     escape_xml(t),
                     escape_xml(&cell.text)
@@ -118,7 +124,7 @@ impl MutableSpreadsheet {
             },
             CellValue::Boolean(b) => {
                 out.push_str(&format!(
-                    r#"<table:table-cell{} office:value-type="boolean" office:boolean-value="{}"><text:p>{}</text:p></table:table-cell>"#,
+                    r#"<table:table-cell{} office:value-type="boolean" office:boolean-value="{}">{annotation}<text:p>{}</text:p></table:table-cell>"#,
                     formula_attr,
                     b,
                     escape_xml(&cell.text)
@@ -127,11 +133,15 @@ impl MutableSpreadsheet {
             CellValue::Empty => {
                 if cell.formula.is_some() {
                     out.push_str(&format!(
-                        r#"<table:table-cell{} office:value-type="float" office:value="0"><text:p>0</text:p></table:table-cell>"#,
+                        r#"<table:table-cell{} office:value-type="float" office:value="0">{annotation}<text:p>0</text:p></table:table-cell>"#,
                         formula_attr
                     ));
-                } else {
+                } else if annotation.is_empty() {
                     out.push_str("<table:table-cell/>");
+                } else {
+                    out.push_str(&format!(
+                        "<table:table-cell>{annotation}</table:table-cell>"
+                    ));
                 }
             },
         }
@@ -153,6 +163,7 @@ impl MutableSpreadsheet {
     /// ```
     pub fn from_spreadsheet(mut spreadsheet: Spreadsheet) -> Result<Self> {
         let sheets = spreadsheet.sheets()?;
+        let named_ranges = spreadsheet.named_ranges()?;
         let metadata = spreadsheet.metadata()?;
         let mimetype = "application/vnd.oasis.opendocument.spreadsheet".to_string();
 
@@ -162,6 +173,7 @@ impl MutableSpreadsheet {
 
         Ok(Self {
             sheets,
+            named_ranges,
             metadata,
             mimetype,
             styles_xml: None,
@@ -172,6 +184,7 @@ impl MutableSpreadsheet {
     pub fn new() -> Self {
         Self {
             sheets: Vec::new(),
+            named_ranges: Vec::new(),
             metadata: Metadata::default(),
             mimetype: "application/vnd.oasis.opendocument.spreadsheet".to_string(),
             styles_xml: None,
@@ -188,6 +201,16 @@ impl MutableSpreadsheet {
         &mut self.sheets
     }
 
+    /// Get named ranges.
+    pub fn named_ranges(&self) -> &[NamedRange] {
+        &self.named_ranges
+    }
+
+    /// Get mutable reference to named ranges.
+    pub fn named_ranges_mut(&mut self) -> &mut Vec<NamedRange> {
+        &mut self.named_ranges
+    }
+
     /// Get metadata.
     pub fn metadata(&self) -> &Metadata {
         &self.metadata
@@ -198,11 +221,25 @@ impl MutableSpreadsheet {
         &mut self.metadata
     }
 
+    /// Set a user-defined metadata field.
+    ///
+    /// The field is written as a `meta:user-defined` element whose value type
+    /// (string, float, date or boolean) follows the value.
+    pub fn set_user_defined(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<CustomPropertyValue>,
+    ) {
+        self.metadata
+            .custom_properties
+            .insert(name.into(), value.into());
+    }
+
     /// Add a new sheet.
     pub fn add_sheet(&mut self, name: &str) -> Result<()> {
         let sheet = Sheet {
             name: name.to_string(),
-            rows: Vec::new(),
+            ..Default::default()
         };
         self.sheets.push(sheet);
         Ok(())
@@ -409,14 +446,22 @@ impl MutableSpreadsheet {
 
         for sheet in &self.sheets {
             let escaped_name = escape_xml(&sheet.name);
-            body.push_str(&format!(r#"<table:table table:name="{}">"#, escaped_name));
+            let protection = sheet
+                .protection
+                .as_ref()
+                .map(SheetProtection::table_attributes)
+                .unwrap_or_default();
+            body.push_str(&format!(
+                r#"<table:table table:name="{}"{}>"#,
+                escaped_name, protection
+            ));
 
             Self::push_table_columns(&mut body, Self::sheet_max_cols(sheet));
 
-            for row in &sheet.rows {
+            for (row_index, row) in sheet.rows.iter().enumerate() {
                 body.push_str("<table:table-row>");
-                for cell in &row.cells {
-                    Self::push_cell(&mut body, cell);
+                for (col, cell) in row.cells.iter().enumerate() {
+                    Self::push_cell(&mut body, cell, sheet.comment(row_index, col));
                 }
                 body.push_str("</table:table-row>");
             }
@@ -424,6 +469,8 @@ impl MutableSpreadsheet {
             body.push_str("</table:table>");
         }
 
+        body.push_str(&named_expressions_xml(&self.named_ranges));
+
         let of_ns = if self.has_formulas() {
             " xmlns:of=\"urn:oasis:names:tc:opendocument:xmlns:of:1.2\""
         } else {
@@ -432,7 +479,7 @@ impl MutableSpreadsheet {
 
         let mut out = String::with_capacity(body.len() + 256);
         out.push_str(
-            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0""#,
+            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-content xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0" xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/""#,
         );
         out.push_str(of_ns);
         out.push_str(
@@ -444,11 +491,7 @@ impl MutableSpreadsheet {
     }
 
     fn generate_meta_xml(&self) -> String {
-        let now = chrono::Utc::now().to_rfc3339();
-        format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" office:version="1.3"><office:meta><meta:generator>Litchi/0.0.1</meta:generator><dc:date>{}</dc:date></office:meta></office:document-meta>"#,
-            now
-        )
+        meta_xml(&self.metadata)
     }
 
     /// Save the modified spreadsheet.
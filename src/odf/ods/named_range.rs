@@ -0,0 +1,118 @@
+//! Named ranges for ODS spreadsheets.
+//!
+//! Named ranges are declared in `table:named-expressions` after the tables
+//! of the spreadsheet. Each `table:named-range` pairs a name with an ODF
+//! cell range address such as `$Sheet1.$A$1:.$B$10`.
+
+use crate::common::xml::escape_xml;
+
+/// A named cell range of the spreadsheet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamedRange {
+    /// Name of the range
+    pub name: String,
+    /// ODF cell range address, e.g. `$Sheet1.$A$1:.$B$10`
+    pub range: String,
+    /// Cell that relative parts of the address are based on
+    pub base_cell: Option<String>,
+}
+
+impl NamedRange {
+    /// Create a named range.
+    ///
+    /// The range may be given as an ODF cell range address
+    /// (`$Sheet1.$A$1:.$B$10`) or in Excel notation (`Sheet1!$A$1:$B$10`),
+    /// which is converted.
+    pub fn new(name: &str, range: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            range: to_range_address(range),
+            base_cell: None,
+        }
+    }
+
+    /// Get the `table:named-range` element of the range.
+    pub(super) fn to_xml(&self) -> String {
+        // The base cell is required; default to the start of the range
+        let base_cell = self
+            .base_cell
+            .clone()
+            .unwrap_or_else(|| self.range.split(':').next().unwrap_or_default().to_string());
+        format!(
+            r#"<table:named-range table:name="{}" table:base-cell-address="{}" table:cell-range-address="{}"/>"#,
+            escape_xml(&self.name),
+            escape_xml(&base_cell),
+            escape_xml(&self.range)
+        )
+    }
+}
+
+/// Get the `table:named-expressions` element for a list of named ranges.
+pub(super) fn named_expressions_xml(ranges: &[NamedRange]) -> String {
+    if ranges.is_empty() {
+        return String::new();
+    }
+
+    let mut out = String::from("<table:named-expressions>");
+    for range in ranges {
+        out.push_str(&range.to_xml());
+    }
+    out.push_str("</table:named-expressions>");
+    out
+}
+
+/// Convert an Excel-style range (`Sheet1!A1:B2`) into an ODF cell range
+/// address (`Sheet1.A1:.B2`); ODF addresses are returned unchanged
+fn to_range_address(range: &str) -> String {
+    let range = range.trim();
+    let Some((sheet, cells)) = range.rsplit_once('!') else {
+        return range.to_string();
+    };
+
+    let sheet = match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+        Some(quoted) => format!("'{}'", quoted),
+        None => sheet.to_string(),
+    };
+    let mut out = String::new();
+    for (index, cell) in cells.split(':').enumerate() {
+        if index == 0 {
+            out.push('$');
+            out.push_str(&sheet);
+        } else {
+            out.push(':');
+        }
+        out.push('.');
+        out.push_str(cell);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_address_conversion() {
+        assert_eq!(
+            NamedRange::new("Data", "Sheet1!$A$1:$B$10").range,
+            "$Sheet1.$A$1:.$B$10"
+        );
+        assert_eq!(
+            NamedRange::new("One", "'My Sheet'!C3").range,
+            "$'My Sheet'.C3"
+        );
+        assert_eq!(
+            NamedRange::new("Odf", "$Sheet1.$A$1:.$A$5").range,
+            "$Sheet1.$A$1:.$A$5"
+        );
+    }
+
+    #[test]
+    fn test_named_expressions_xml() {
+        assert!(named_expressions_xml(&[]).is_empty());
+        assert_eq!(
+            named_expressions_xml(&[NamedRange::new("Data", "$Sheet1.$A$1:.$B$2")]),
+            r#"<table:named-expressions><table:named-range table:name="Data" table:base-cell-address="$Sheet1.$A$1" table:cell-range-address="$Sheet1.$A$1:.$B$2"/></table:named-expressions>"#
+        );
+    }
+}
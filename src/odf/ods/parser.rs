@@ -1,9 +1,17 @@
 //! ODS-specific parsing utilities.
 
-use super::{Cell, CellValue, Row, Sheet};
-use crate::common::{Error, Result};
+use super::{Cell, CellComment, CellValue, NamedRange, Row, Sheet, SheetProtection};
+use crate::common::{Error, Protection, ProtectionKind, Result, trace};
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesRef, Event};
+
+/// Element of an `office:annotation` whose text is being read
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum AnnotationField {
+    Creator,
+    Date,
+    Paragraph,
+}
 
 /// Parser for ODS-specific structures.
 ///
@@ -14,6 +22,13 @@ pub(crate) struct OdsParser;
 impl OdsParser {
     /// Parse all sheets from ODS content.xml
     pub fn parse_sheets(xml_content: &str) -> Result<Vec<Sheet>> {
+        let _span = trace::info_span!(
+            "parse_part",
+            part = "content.xml",
+            size = xml_content.len(),
+            content = "sheets"
+        )
+        .entered();
         let mut reader = Reader::from_str(xml_content);
         let mut buf = Vec::new();
         let mut sheets = Vec::new();
@@ -25,13 +40,36 @@ impl OdsParser {
         let mut in_text_element = false;
         let mut text_content = String::new();
 
+        // Comments of the current row, with their column index
+        let mut row_comments: Vec<CellComment> = Vec::new();
+        let mut current_annotation: Option<(CellComment, Vec<String>)> = None;
+        let mut annotation_field: Option<AnnotationField> = None;
+
         loop {
             match reader.read_event_into(&mut buf) {
                 Ok(Event::Start(ref e)) => match e.name().as_ref() {
                     b"table:table" => {
                         let name = Self::extract_table_name(e)?;
-                        current_sheet = Some(SheetBuilder::new(name));
+                        let mut sheet_builder = SheetBuilder::new(name);
+                        sheet_builder.protection = Self::parse_protection(e)?;
+                        current_sheet = Some(sheet_builder);
+                    },
+                    b"office:annotation" if current_cell.is_some() => {
+                        current_annotation = Some((CellComment::default(), Vec::new()));
+                    },
+                    b"dc:creator" if current_annotation.is_some() => {
+                        annotation_field = Some(AnnotationField::Creator);
+                    },
+                    b"dc:date" if current_annotation.is_some() => {
+                        annotation_field = Some(AnnotationField::Date);
+                    },
+                    b"text:p" if current_annotation.is_some() => {
+                        if let Some((_, paragraphs)) = &mut current_annotation {
+                            paragraphs.push(String::new());
+                        }
+                        annotation_field = Some(AnnotationField::Paragraph);
                     },
+                    b"text:span" if current_annotation.is_some() => {},
                     b"table:table-row" => {
                         if current_sheet.is_some() {
                             current_row = Some(RowBuilder::new());
@@ -54,14 +92,58 @@ impl OdsParser {
                     },
                     _ => {},
                 },
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"table:table" => {
+                    let mut sheet_builder = SheetBuilder::new(Self::extract_table_name(e)?);
+                    sheet_builder.protection = Self::parse_protection(e)?;
+                    sheets.push(sheet_builder.build());
+                },
+                Ok(Event::Empty(ref e)) if e.name().as_ref() == b"table:table-cell" => {
+                    if let Some(ref mut row_builder) = current_row {
+                        let cell_builder = Self::parse_cell_attributes(e)?;
+                        if cell_builder.value_type.is_some() || cell_builder.formula.is_some() {
+                            for _ in 0..cell_builder.repeated {
+                                row_builder.add_cell(cell_builder.build(""));
+                            }
+                        } else {
+                            row_builder.skip_cells(cell_builder.repeated);
+                        }
+                    }
+                },
                 Ok(Event::Text(ref t)) => {
-                    if in_text_element && current_cell.is_some() {
+                    if let (Some(annotation), Some(field)) =
+                        (&mut current_annotation, annotation_field)
+                    {
+                        let text = t.decode().unwrap_or_default();
+                        Self::push_annotation_text(annotation, field, &text);
+                    } else if in_text_element && current_cell.is_some() {
                         let text = String::from_utf8(t.to_vec()).unwrap_or_default();
                         text_content.push_str(&text);
                     }
                 },
+                Ok(Event::GeneralRef(ref r)) => {
+                    let resolved = Self::resolve_reference(r);
+                    if let (Some(annotation), Some(field)) =
+                        (&mut current_annotation, annotation_field)
+                    {
+                        Self::push_annotation_text(annotation, field, &resolved);
+                    } else if in_text_element && current_cell.is_some() {
+                        text_content.push_str(&resolved);
+                    }
+                },
                 Ok(Event::End(ref e)) => {
                     match e.name().as_ref() {
+                        b"office:annotation" => {
+                            if let Some((mut comment, paragraphs)) = current_annotation.take() {
+                                comment.text = paragraphs.join("\n");
+                                comment.col = current_row.as_ref().map_or(0, RowBuilder::next_col);
+                                row_comments.push(comment);
+                            }
+                            annotation_field = None;
+                        },
+                        b"dc:creator" | b"dc:date" | b"text:p" if current_annotation.is_some() => {
+                            annotation_field = None;
+                        },
+                        b"text:span" if current_annotation.is_some() => {},
                         b"text:p" | b"text:span" => {
                             if in_text_element {
                                 in_text_element = false;
@@ -87,9 +169,15 @@ impl OdsParser {
                             if let Some(row_builder) = current_row.take() {
                                 let row = row_builder.build();
                                 if let Some(ref mut sheet_builder) = current_sheet {
+                                    let row_index = sheet_builder.rows.len();
+                                    for mut comment in row_comments.drain(..) {
+                                        comment.row = row_index;
+                                        sheet_builder.comments.push(comment);
+                                    }
                                     sheet_builder.add_row(row);
                                 }
                             }
+                            row_comments.clear();
                         },
                         b"table:table" => {
                             if let Some(sheet_builder) = current_sheet.take() {
@@ -102,7 +190,12 @@ impl OdsParser {
                 },
                 Ok(Event::Eof) => break,
                 Err(e) => {
-                    return Err(Error::InvalidFormat(format!("XML parsing error: {}", e)));
+                    return Err(Error::xml_at(
+                        Some("content.xml"),
+                        xml_content.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
                 },
                 _ => {},
             }
@@ -112,6 +205,162 @@ impl OdsParser {
         Ok(sheets)
     }
 
+    /// Parse the named ranges declared in `table:named-expressions`
+    pub fn parse_named_ranges(xml_content: &str) -> Result<Vec<NamedRange>> {
+        let mut reader = Reader::from_str(xml_content);
+        let mut buf = Vec::new();
+        let mut ranges = Vec::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e))
+                    if e.name().as_ref() == b"table:named-range" =>
+                {
+                    let mut range = NamedRange::default();
+                    for attr_result in e.attributes() {
+                        let attr = attr_result
+                            .map_err(|_| Error::InvalidFormat("Invalid attribute".to_string()))?;
+                        let value = attr
+                            .decode_and_unescape_value(e.decoder())
+                            .map_err(|_| Error::InvalidFormat("Invalid attribute".to_string()))?
+                            .into_owned();
+                        match attr.key.as_ref() {
+                            b"table:name" => range.name = value,
+                            b"table:cell-range-address" => range.range = value,
+                            b"table:base-cell-address" => range.base_cell = Some(value),
+                            _ => {},
+                        }
+                    }
+                    ranges.push(range);
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    return Err(Error::xml_at(
+                        Some("content.xml"),
+                        xml_content.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
+                },
+                _ => {},
+            }
+            buf.clear();
+        }
+
+        Ok(ranges)
+    }
+
+    /// Parse the structure protection of the spreadsheet and the protection
+    /// of its sheets into a single restriction.
+    ///
+    /// A protected structure or any protected sheet makes the spreadsheet
+    /// read-only; it has a password if any of them has a protection key.
+    pub fn parse_document_protection(xml_content: &str) -> Result<Protection> {
+        let mut reader = Reader::from_str(xml_content);
+        let mut buf = Vec::new();
+        let mut protection = Protection::default();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                    b"office:spreadsheet" => {
+                        let mut structure = Protection::default();
+                        for attr in e.attributes().flatten() {
+                            match attr.key.as_ref() {
+                                b"table:structure-protected" if attr.value.as_ref() == b"true" => {
+                                    structure.kind = ProtectionKind::ReadOnly;
+                                },
+                                b"table:protection-key" => structure.has_password = true,
+                                _ => {},
+                            }
+                        }
+                        if structure.is_protected() {
+                            protection = protection.or(structure);
+                        }
+                    },
+                    b"table:table" => {
+                        if let Some(sheet) = Self::parse_protection(e)? {
+                            protection = protection.or(Protection {
+                                kind: ProtectionKind::ReadOnly,
+                                has_password: sheet.protection_key.is_some(),
+                            });
+                        }
+                    },
+                    _ => {},
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => {
+                    return Err(Error::xml_at(
+                        Some("content.xml"),
+                        xml_content.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
+                },
+                _ => {},
+            }
+            buf.clear();
+        }
+
+        Ok(protection)
+    }
+
+    /// Append text to the field of an annotation that is being read
+    fn push_annotation_text(
+        annotation: &mut (CellComment, Vec<String>),
+        field: AnnotationField,
+        text: &str,
+    ) {
+        let (comment, paragraphs) = annotation;
+        match field {
+            AnnotationField::Creator => comment.author.get_or_insert_default().push_str(text),
+            AnnotationField::Date => comment.date.get_or_insert_default().push_str(text),
+            AnnotationField::Paragraph => {
+                if let Some(paragraph) = paragraphs.last_mut() {
+                    paragraph.push_str(text);
+                }
+            },
+        }
+    }
+
+    /// Resolve a character or predefined entity reference
+    pub(super) fn resolve_reference(reference: &BytesRef) -> String {
+        match reference.resolve_char_ref() {
+            Ok(Some(ch)) => ch.to_string(),
+            _ => reference
+                .decode()
+                .ok()
+                .and_then(|name| quick_xml::escape::resolve_predefined_entity(&name))
+                .unwrap_or_default()
+                .to_string(),
+        }
+    }
+
+    /// Parse the protection attributes of a table:table element
+    fn parse_protection(e: &quick_xml::events::BytesStart) -> Result<Option<SheetProtection>> {
+        let mut protected = false;
+        let mut protection = SheetProtection::new();
+
+        for attr_result in e.attributes() {
+            let attr =
+                attr_result.map_err(|_| Error::InvalidFormat("Invalid attribute".to_string()))?;
+            let value = || {
+                String::from_utf8(attr.value.to_vec())
+                    .map_err(|_| Error::InvalidFormat("Invalid UTF-8".to_string()))
+            };
+            match attr.key.as_ref() {
+                b"table:protected" => protected = value()? == "true",
+                b"table:protection-key" => protection.protection_key = Some(value()?),
+                b"table:protection-key-digest-algorithm" => {
+                    protection.digest_algorithm = Some(value()?)
+                },
+                _ => {},
+            }
+        }
+
+        Ok(protected.then_some(protection))
+    }
+
     /// Extract table name from table:table element
     fn extract_table_name(e: &quick_xml::events::BytesStart) -> Result<String> {
         for attr_result in e.attributes() {
@@ -156,9 +405,11 @@ impl OdsParser {
                     );
                 },
                 b"table:formula" => {
+                    // Comparison operators are stored escaped (`&lt;`, `&gt;`)
                     formula = Some(
-                        String::from_utf8(attr.value.to_vec())
-                            .map_err(|_| Error::InvalidFormat("Invalid UTF-8".to_string()))?,
+                        attr.decode_and_unescape_value(e.decoder())
+                            .map_err(|_| Error::InvalidFormat("Invalid formula".to_string()))?
+                            .into_owned(),
                     );
                 },
                 b"table:number-columns-repeated" => {
@@ -187,6 +438,8 @@ impl OdsParser {
 pub(crate) struct SheetBuilder {
     name: String,
     rows: Vec<Row>,
+    comments: Vec<CellComment>,
+    protection: Option<SheetProtection>,
 }
 
 impl SheetBuilder {
@@ -194,6 +447,8 @@ impl SheetBuilder {
         Self {
             name,
             rows: Vec::new(),
+            comments: Vec::new(),
+            protection: None,
         }
     }
 
@@ -211,6 +466,10 @@ impl SheetBuilder {
         Sheet {
             name: self.name,
             rows: self.rows,
+            comments: self.comments,
+            protection: self.protection,
+            #[cfg(feature = "ooxml")]
+            charts: Vec::new(),
         }
     }
 }
@@ -218,18 +477,38 @@ impl SheetBuilder {
 /// Builder for constructing Row during parsing
 pub(crate) struct RowBuilder {
     cells: Vec<Cell>,
+    /// Empty cells that are only added once a later cell follows, so that
+    /// trailing repeated empty cells do not widen the row
+    pending_empty: usize,
 }
 
 impl RowBuilder {
     pub fn new() -> Self {
-        Self { cells: Vec::new() }
+        Self {
+            cells: Vec::new(),
+            pending_empty: 0,
+        }
     }
 
     pub fn add_cell(&mut self, mut cell: Cell) {
+        for _ in 0..std::mem::take(&mut self.pending_empty) {
+            let empty = CellBuilder::default().build("");
+            self.add_cell(empty);
+        }
         cell.col = self.cells.len();
         self.cells.push(cell);
     }
 
+    /// Skip empty cells written as `<table:table-cell/>`
+    pub fn skip_cells(&mut self, count: usize) {
+        self.pending_empty += count;
+    }
+
+    /// Column index of the next cell
+    pub fn next_col(&self) -> usize {
+        self.cells.len() + self.pending_empty
+    }
+
     pub fn build(mut self) -> Row {
         // Row index will be set by the parent SheetBuilder
         // For now, set to 0 and update cells
@@ -245,6 +524,7 @@ impl RowBuilder {
 }
 
 /// Builder for constructing Cell during parsing
+#[derive(Default)]
 pub(crate) struct CellBuilder {
     value_type: Option<String>,
     value_str: Option<String>,
@@ -818,4 +1098,66 @@ mod tests {
             },
         }
     }
+
+    #[test]
+    fn test_parse_annotations_and_protection() {
+        let xml = r#"<office:document-content
+    xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0"
+    xmlns:table="urn:oasis:names:tc:opendocument:xmlns:table:1.0"
+    xmlns:text="urn:oasis:names:tc:opendocument:xmlns:text:1.0"
+    xmlns:dc="http://purl.org/dc/elements/1.1/">
+    <office:body>
+        <office:spreadsheet>
+            <table:table table:name="Locked" table:protected="true"
+                table:protection-key="W6ph5Mm5Pz8GgiULbPgzG37mj9g="
+                table:protection-key-digest-algorithm="http://www.w3.org/2000/09/xmldsig#sha1">
+                <table:table-row>
+                    <table:table-cell/>
+                    <table:table-cell office:value-type="float" office:value="5">
+                        <office:annotation>
+                            <dc:creator>Ann &amp; Bob</dc:creator>
+                            <dc:date>2024-03-15T10:00:00</dc:date>
+                            <text:p>Check <text:span>this</text:span></text:p>
+                            <text:p>again</text:p>
+                        </office:annotation>
+                        <text:p>5</text:p>
+                    </table:table-cell>
+                </table:table-row>
+            </table:table>
+            <table:table table:name="Open"/>
+            <table:named-expressions>
+                <table:named-range table:name="Values" table:base-cell-address="$Locked.$A$1"
+                    table:cell-range-address="$Locked.$A$1:.$B$1"/>
+            </table:named-expressions>
+        </office:spreadsheet>
+    </office:body>
+</office:document-content>"#;
+
+        let sheets = OdsParser::parse_sheets(xml).unwrap();
+        let sheet = &sheets[0];
+        assert_eq!(sheet.rows[0].cells[1].text, "5");
+
+        let comment = sheet.comment(0, 1).unwrap();
+        assert_eq!(comment.text, "Check this\nagain");
+        assert_eq!(comment.author.as_deref(), Some("Ann & Bob"));
+        assert_eq!(comment.date.as_deref(), Some("2024-03-15T10:00:00"));
+
+        let protection = sheet.protection.as_ref().unwrap();
+        assert!(protection.verify_password("password"));
+        assert!(sheets[1].protection.is_none());
+        assert_eq!(
+            OdsParser::parse_document_protection(xml).unwrap(),
+            Protection::with_password(ProtectionKind::ReadOnly)
+        );
+
+        let ranges = OdsParser::parse_named_ranges(xml).unwrap();
+        assert_eq!(
+            ranges,
+            [NamedRange {
+                name: "Values".to_string(),
+                range: "$Locked.$A$1:.$B$1".to_string(),
+                base_cell: Some("$Locked.$A$1".to_string()),
+            }]
+        );
+    }
 }
@@ -0,0 +1,127 @@
+//! Sheet protection for ODS spreadsheets.
+//!
+//! A protected table carries `table:protected="true"` and, when a password
+//! is set, a `table:protection-key` holding the Base64-encoded digest of the
+//! password. ODF 1.2 (§19.699) defaults the digest to SHA-1 of the UTF-8
+//! password; LibreOffice also writes SHA-256 and names it in
+//! `table:protection-key-digest-algorithm`.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64_ENGINE;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+/// Protection settings of a sheet.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SheetProtection {
+    /// Base64-encoded password digest, if the protection has a password
+    pub protection_key: Option<String>,
+    /// URI of the digest algorithm; `None` means SHA-1
+    pub digest_algorithm: Option<String>,
+}
+
+impl SheetProtection {
+    /// Digest algorithm URI of the legacy SHA-1 protection key
+    pub const SHA1_DIGEST_ALGORITHM: &str = "http://www.w3.org/2000/09/xmldsig#sha1";
+
+    /// Digest algorithm URI of the SHA-256 protection key
+    pub const SHA256_DIGEST_ALGORITHM: &str = "http://www.w3.org/2000/09/xmldsig#sha256";
+
+    /// Create protection without a password.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create password protection using the legacy SHA-1 key of the ODF
+    /// specification.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use litchi::odf::SheetProtection;
+    ///
+    /// let protection = SheetProtection::with_password("secret");
+    /// assert!(protection.verify_password("secret"));
+    /// assert!(!protection.verify_password("Secret"));
+    /// ```
+    pub fn with_password(password: &str) -> Self {
+        Self {
+            protection_key: Some(BASE64_ENGINE.encode(Sha1::digest(password.as_bytes()))),
+            digest_algorithm: Some(Self::SHA1_DIGEST_ALGORITHM.to_string()),
+        }
+    }
+
+    /// Check if a password unlocks the sheet.
+    ///
+    /// Protection without a key accepts any password. Keys using an
+    /// unsupported digest algorithm never match.
+    pub fn verify_password(&self, password: &str) -> bool {
+        let Some(key) = &self.protection_key else {
+            return true;
+        };
+
+        let digest = match self.digest_algorithm.as_deref() {
+            None => Sha1::digest(password.as_bytes()).to_vec(),
+            Some(uri) if uri.ends_with("#sha1") => Sha1::digest(password.as_bytes()).to_vec(),
+            Some(uri) if uri.ends_with("#sha256") => Sha256::digest(password.as_bytes()).to_vec(),
+            Some(_) => return false,
+        };
+        BASE64_ENGINE.encode(digest) == *key
+    }
+
+    /// Get the protection attributes of a `table:table` element.
+    pub(super) fn table_attributes(&self) -> String {
+        let mut attrs = String::from(r#" table:protected="true""#);
+        if let Some(key) = &self.protection_key {
+            attrs.push_str(&format!(
+                r#" table:protection-key="{}""#,
+                crate::common::xml::escape_xml(key)
+            ));
+            if let Some(algorithm) = &self.digest_algorithm {
+                attrs.push_str(&format!(
+                    r#" table:protection-key-digest-algorithm="{}""#,
+                    crate::common::xml::escape_xml(algorithm)
+                ));
+            }
+        }
+        attrs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_legacy_key() {
+        // SHA-1 of "password"
+        let protection = SheetProtection::with_password("password");
+        assert_eq!(
+            protection.protection_key.as_deref(),
+            Some("W6ph5Mm5Pz8GgiULbPgzG37mj9g=")
+        );
+        assert!(protection.verify_password("password"));
+        assert!(!protection.verify_password("wrong"));
+    }
+
+    #[test]
+    fn test_verify_sha256_and_unkeyed() {
+        let protection = SheetProtection {
+            protection_key: Some("XohImNooBHFR0OVvjcYpJ3NgPQ1qq73WKhHvch0VQtg=".to_string()),
+            digest_algorithm: Some(SheetProtection::SHA256_DIGEST_ALGORITHM.to_string()),
+        };
+        assert!(protection.verify_password("password"));
+        assert!(SheetProtection::new().verify_password("anything"));
+    }
+
+    #[test]
+    fn test_table_attributes() {
+        assert_eq!(
+            SheetProtection::new().table_attributes(),
+            r#" table:protected="true""#
+        );
+        let attrs = SheetProtection::with_password("password").table_attributes();
+        assert!(attrs.contains(r#"table:protection-key="W6ph5Mm5Pz8GgiULbPgzG37mj9g=""#));
+        assert!(attrs.contains(SheetProtection::SHA1_DIGEST_ALGORITHM));
+    }
+}
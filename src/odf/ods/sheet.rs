@@ -1,17 +1,24 @@
 //! Sheet structures for ODS spreadsheets.
 
-use super::Row;
+use super::{CellComment, Row, SheetProtection};
 use crate::common::Result;
 
 /// A sheet (worksheet) in an ODS spreadsheet.
 ///
 /// Sheets contain rows of cells and have a name for identification.
-#[derive(Clone)]
+#[derive(Clone, Default)]
 pub struct Sheet {
     /// Sheet name
     pub name: String,
     /// Rows in this sheet
     pub rows: Vec<Row>,
+    /// Comments attached to cells of this sheet
+    pub comments: Vec<CellComment>,
+    /// Protection settings, if the sheet is protected
+    pub protection: Option<SheetProtection>,
+    /// Charts embedded in this sheet
+    #[cfg(feature = "ooxml")]
+    pub charts: Vec<crate::ooxml::charts::Chart>,
 }
 
 impl Sheet {
@@ -45,6 +52,49 @@ impl Sheet {
             .unwrap_or(0);
         Ok(max_cols)
     }
+
+    /// Get the comment attached to a cell.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - Row index (0-based)
+    /// * `col` - Column index (0-based)
+    pub fn comment(&self, row: usize, col: usize) -> Option<&CellComment> {
+        self.comments
+            .iter()
+            .find(|comment| comment.row == row && comment.col == col)
+    }
+
+    /// Check if the sheet is protected.
+    pub fn is_protected(&self) -> bool {
+        self.protection.is_some()
+    }
+
+    /// Get the charts embedded in the sheet.
+    ///
+    /// Charts are read from the `Object N` sub-documents that the sheet's
+    /// `draw:object` frames refer to, and use the same model as charts read
+    /// from .xlsx files. Series data is read from the cells of the spreadsheet.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use litchi::odf::Spreadsheet;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let mut spreadsheet = Spreadsheet::open("report.ods")?;
+    /// for sheet in spreadsheet.sheets()? {
+    ///     for chart in sheet.charts()? {
+    ///         println!("{}: {} series", sheet.name()?, chart.plot_area.series().count());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "ooxml")]
+    pub fn charts(&self) -> Result<&[crate::ooxml::charts::Chart]> {
+        Ok(&self.charts)
+    }
 }
 
 #[cfg(test)]
@@ -58,6 +108,7 @@ mod tests {
         let sheet = Sheet {
             name: "Sheet1".to_string(),
             rows: vec![],
+            ..Default::default()
         };
         assert_eq!(sheet.name().unwrap(), "Sheet1");
         assert_eq!(sheet.row_count().unwrap(), 0);
@@ -69,6 +120,7 @@ mod tests {
         let sheet = Sheet {
             name: "Test Sheet".to_string(),
             rows: vec![],
+            ..Default::default()
         };
         assert_eq!(sheet.name().unwrap(), "Test Sheet");
     }
@@ -87,6 +139,7 @@ mod tests {
                     index: 1,
                 },
             ],
+            ..Default::default()
         };
         assert_eq!(sheet.row_count().unwrap(), 2);
         let rows = sheet.rows().unwrap();
@@ -144,6 +197,7 @@ mod tests {
                     index: 1,
                 },
             ],
+            ..Default::default()
         };
         // Should return max column count across all rows
         assert_eq!(sheet.column_count().unwrap(), 3);
@@ -154,6 +208,7 @@ mod tests {
         let sheet = Sheet {
             name: "Empty".to_string(),
             rows: vec![],
+            ..Default::default()
         };
         assert_eq!(sheet.column_count().unwrap(), 0);
     }
@@ -202,6 +257,7 @@ mod tests {
                     index: 1,
                 },
             ],
+            ..Default::default()
         };
 
         assert_eq!(sheet.name().unwrap(), "Data");
@@ -219,6 +275,7 @@ mod tests {
         let sheet = Sheet {
             name: "Original".to_string(),
             rows: vec![],
+            ..Default::default()
         };
         let cloned = sheet.clone();
         assert_eq!(cloned.name().unwrap(), "Original");
@@ -1,9 +1,11 @@
 //! Main Spreadsheet structure and implementation.
 
-use super::Sheet;
-use crate::common::{Error, Metadata, Result};
+use super::workbook::SheetGrids;
+use super::{NamedRange, Sheet};
+use crate::common::{Error, Metadata, Protection, Result};
 use crate::odf::core::{Content, Meta, OwnedPackage, Styles};
 use std::path::Path;
+use std::sync::OnceLock;
 
 /// An OpenDocument spreadsheet (.ods).
 ///
@@ -34,11 +36,20 @@ use std::path::Path;
 /// ```
 pub struct Spreadsheet {
     package: OwnedPackage,
-    #[allow(dead_code)]
     content: Content,
     #[allow(dead_code)]
     styles: Option<Styles>,
     meta: Option<Meta>,
+    /// Cell values converted for the `WorkbookTrait` API, parsed on first use
+    grids: OnceLock<std::result::Result<SheetGrids, String>>,
+}
+
+impl std::fmt::Debug for Spreadsheet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Spreadsheet")
+            .field("grids", &self.grids)
+            .finish_non_exhaustive()
+    }
 }
 
 impl Spreadsheet {
@@ -89,7 +100,49 @@ impl Spreadsheet {
     /// # }
     /// ```
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
-        let owned_package = OwnedPackage::from_bytes(bytes)?;
+        Self::from_package(OwnedPackage::from_bytes(bytes)?)
+    }
+
+    /// Open a password-protected ODS spreadsheet.
+    ///
+    /// Entries encrypted with Blowfish CFB or AES-CBC, as written by
+    /// OpenOffice.org and LibreOffice, are decrypted in memory. Files that are
+    /// not encrypted open normally and the password is ignored.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::IncorrectPassword`] if the password does not decrypt the file
+    /// - [`Error::UnsupportedEncryption`] for other encryption schemes, such as
+    ///   the Argon2id and AES-GCM encryption of LibreOffice 24.2 and later
+    /// - [`Error::CorruptedFile`] if an encrypted entry is damaged
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use litchi::odf::Spreadsheet;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let mut spreadsheet = Spreadsheet::open_with_password("protected.ods", "secret")?;
+    /// println!("{} sheets", spreadsheet.sheet_count()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "odf_encryption")]
+    pub fn open_with_password<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        let bytes = std::fs::read(path.as_ref())?;
+        Self::from_bytes_with_password(bytes, password)
+    }
+
+    /// Create a Spreadsheet from the bytes of a possibly password-protected file.
+    ///
+    /// See [`Spreadsheet::open_with_password`] for details.
+    #[cfg(feature = "odf_encryption")]
+    pub fn from_bytes_with_password(bytes: Vec<u8>, password: &str) -> Result<Self> {
+        Self::from_package(OwnedPackage::from_bytes_with_password(bytes, password)?)
+    }
+
+    /// Parse the parts of an opened package.
+    fn from_package(owned_package: OwnedPackage) -> Result<Self> {
         let package = owned_package.package()?;
 
         // Verify this is a spreadsheet
@@ -124,6 +177,7 @@ impl Spreadsheet {
             content,
             styles,
             meta,
+            grids: OnceLock::new(),
         })
     }
 
@@ -144,6 +198,7 @@ impl Spreadsheet {
     /// Get all sheets in the spreadsheet.
     ///
     /// Returns a vector of `Sheet` objects representing all sheets in the document.
+    /// With the `ooxml` feature, the sheets also carry their embedded charts.
     pub fn sheets(&mut self) -> Result<Vec<Sheet>> {
         use super::parser::OdsParser;
 
@@ -151,7 +206,21 @@ impl Spreadsheet {
         let content_bytes = package.get_file("content.xml")?;
         let content = Content::from_bytes(&content_bytes)?;
 
-        OdsParser::parse_sheets(content.xml_content())
+        #[cfg_attr(not(feature = "ooxml"), allow(unused_mut))]
+        let mut sheets = OdsParser::parse_sheets(content.xml_content())?;
+        #[cfg(feature = "ooxml")]
+        super::chart::load_charts(&self.package, content.xml_content(), &mut sheets)?;
+        Ok(sheets)
+    }
+
+    /// Get the named ranges defined in the spreadsheet.
+    ///
+    /// Ranges are returned with their ODF cell range addresses, such as
+    /// `$Sheet1.$A$1:.$B$10`.
+    pub fn named_ranges(&self) -> Result<Vec<NamedRange>> {
+        use super::parser::OdsParser;
+
+        OdsParser::parse_named_ranges(self.content.xml_content())
     }
 
     /// Get a sheet by name.
@@ -178,6 +247,20 @@ impl Spreadsheet {
         Ok(sheets.into_iter().nth(index))
     }
 
+    /// Get the sheets converted for the `WorkbookTrait` API.
+    pub(super) fn grids(&self) -> std::result::Result<&SheetGrids, String> {
+        use super::parser::OdsParser;
+
+        self.grids
+            .get_or_init(|| {
+                OdsParser::parse_sheets(self.content.xml_content())
+                    .map(|sheets| SheetGrids::from_sheets(&sheets))
+                    .map_err(|e| format!("Failed to parse sheets: {}", e))
+            })
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
     /// Extract all text content from the spreadsheet.
     ///
     /// Returns text from all cells, separated by newlines.
@@ -265,6 +348,23 @@ impl Spreadsheet {
         }
     }
 
+    /// Get the editing protection of the spreadsheet.
+    ///
+    /// Reads the `LoadReadonly` recommendation and the change recording and
+    /// modify passwords from settings.xml, and the structure and sheet
+    /// protection from content.xml. A protected structure or sheet makes the
+    /// spreadsheet [`ReadOnly`](crate::common::ProtectionKind::ReadOnly).
+    pub fn protection(&self) -> Result<Protection> {
+        let settings = self
+            .package
+            .settings()?
+            .map(|settings| settings.protection())
+            .unwrap_or_default();
+        let content =
+            super::parser::OdsParser::parse_document_protection(self.content.xml_content())?;
+        Ok(settings.or(content))
+    }
+
     // Note: For spreadsheet modification operations, see `MutableSpreadsheet` which provides
     // full CRUD operations on sheets, rows, and cells including set_cell, clear_cell, add/remove
     // rows and sheets.
@@ -0,0 +1,430 @@
+//! Unified workbook access for ODS spreadsheets.
+//!
+//! Implements [`WorkbookTrait`] for [`Spreadsheet`] so that the shared sheet
+//! API and [`FormulaEvaluator`](crate::sheet::FormulaEvaluator) work over ODS
+//! files. Cell formulas are translated from OpenFormula to the Excel syntax
+//! the evaluator understands; values computed by the producing application
+//! are kept as cached results.
+
+use super::formula::to_excel_formula;
+use super::{CellValue as OdsValue, Sheet, Spreadsheet};
+use crate::sheet::text::TextCell;
+use crate::sheet::text::iterators::{TextCellIterator, TextRowIterator};
+use crate::sheet::{
+    Cell, CellIterator, CellValue, DateSystem, Result as SheetResult, RowIterator, WorkbookTrait,
+    Worksheet, WorksheetIterator,
+};
+use chrono::{NaiveDate, NaiveDateTime};
+use std::borrow::Cow;
+
+/// Cell values of all sheets, converted to the unified representation
+#[derive(Debug, Default)]
+pub(super) struct SheetGrids {
+    names: Vec<String>,
+    rows: Vec<Vec<Vec<CellValue>>>,
+}
+
+impl SheetGrids {
+    /// Convert parsed ODS sheets into dense grids of unified cell values
+    pub(super) fn from_sheets(sheets: &[Sheet]) -> Self {
+        let mut grids = Self::default();
+
+        for sheet in sheets {
+            let mut rows: Vec<Vec<CellValue>> = Vec::with_capacity(sheet.rows.len());
+            for row in &sheet.rows {
+                for cell in &row.cells {
+                    if rows.len() <= cell.row {
+                        rows.resize_with(cell.row + 1, Vec::new);
+                    }
+                    let cells = &mut rows[cell.row];
+                    if cells.len() <= cell.col {
+                        cells.resize(cell.col + 1, CellValue::Empty);
+                    }
+                    cells[cell.col] = convert_cell(&cell.value, cell.formula.as_deref());
+                }
+            }
+
+            grids.names.push(sheet.name.clone());
+            grids.rows.push(rows);
+        }
+
+        grids
+    }
+}
+
+/// Convert an ODS cell into a unified cell value
+fn convert_cell(value: &OdsValue, formula: Option<&str>) -> CellValue {
+    let value = convert_value(value);
+
+    match formula {
+        Some(formula) => {
+            // Untranslatable formulas are kept as written; the evaluator
+            // reports them as unsupported
+            let formula = to_excel_formula(formula).unwrap_or_else(|| formula.to_string());
+            let cached_value = (!matches!(value, CellValue::Empty)).then(|| Box::new(value));
+            CellValue::Formula {
+                formula,
+                cached_value,
+                is_array: false,
+                array_range: None,
+            }
+        },
+        None => value,
+    }
+}
+
+/// Convert a typed ODS value into a unified cell value
+fn convert_value(value: &OdsValue) -> CellValue {
+    match value {
+        OdsValue::Empty => CellValue::Empty,
+        OdsValue::Text(text) => CellValue::String(text.clone()),
+        OdsValue::Number(n) | OdsValue::Currency(n, _) | OdsValue::Percentage(n) => {
+            CellValue::Float(*n)
+        },
+        OdsValue::Boolean(b) => CellValue::Bool(*b),
+        OdsValue::Date(date) => date_serial(date)
+            .map(CellValue::DateTime)
+            .unwrap_or_else(|| CellValue::String(date.clone())),
+        OdsValue::Time(time) => duration_days(time)
+            .map(CellValue::DateTime)
+            .unwrap_or_else(|| CellValue::String(time.clone())),
+    }
+}
+
+/// Convert an `office:date-value` (ISO 8601 date or date-time) into a
+/// serial number in the 1900 date system
+fn date_serial(value: &str) -> Option<f64> {
+    let datetime = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f")
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })?;
+
+    Some(DateSystem::Excel1900.datetime_to_serial(datetime))
+}
+
+/// Convert an `office:time-value` (ISO 8601 duration such as `PT12H30M00S`)
+/// into a fraction of days
+fn duration_days(value: &str) -> Option<f64> {
+    let rest = value.strip_prefix("PT")?;
+    let mut seconds = 0.0;
+    let mut number = String::new();
+
+    for c in rest.chars() {
+        match c {
+            '0'..='9' | '.' => number.push(c),
+            'H' | 'M' | 'S' => {
+                let amount: f64 = number.parse().ok()?;
+                number.clear();
+                seconds += amount
+                    * match c {
+                        'H' => 3600.0,
+                        'M' => 60.0,
+                        _ => 1.0,
+                    };
+            },
+            _ => return None,
+        }
+    }
+
+    number.is_empty().then_some(seconds / 86_400.0)
+}
+
+/// Worksheet view over the converted cells of one ODS sheet
+struct OdsWorksheet<'a> {
+    name: &'a str,
+    rows: &'a Vec<Vec<CellValue>>,
+}
+
+impl<'a> Worksheet for OdsWorksheet<'a> {
+    fn name(&self) -> &str {
+        self.name
+    }
+
+    fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn column_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    fn dimensions(&self) -> Option<(u32, u32, u32, u32)> {
+        if self.rows.is_empty() {
+            None
+        } else {
+            Some((1, 1, self.rows.len() as u32, self.column_count() as u32))
+        }
+    }
+
+    fn cell(&self, row: u32, column: u32) -> SheetResult<Box<dyn Cell + '_>> {
+        let value = self.cell_value(row, column)?.into_owned();
+        Ok(Box::new(TextCell::new(row, column, value)))
+    }
+
+    fn cell_by_coordinate(&self, coordinate: &str) -> SheetResult<Box<dyn Cell + '_>> {
+        let split = coordinate
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(coordinate.len());
+        let (letters, digits) = coordinate.split_at(split);
+
+        if letters.is_empty() || !letters.chars().all(|c| c.is_ascii_alphabetic()) {
+            return Err(format!("Invalid coordinate: {}", coordinate).into());
+        }
+        let column = letters.chars().fold(0u32, |acc, c| {
+            acc * 26 + (c.to_ascii_uppercase() as u32 - 'A' as u32 + 1)
+        });
+        let row = digits
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid row number in coordinate: {}", coordinate))?;
+
+        self.cell(row, column)
+    }
+
+    fn cells(&self) -> Box<dyn CellIterator<'_> + '_> {
+        Box::new(TextCellIterator::new(self.rows))
+    }
+
+    fn rows(&self) -> Box<dyn RowIterator<'_> + '_> {
+        Box::new(TextRowIterator::new(self.rows))
+    }
+
+    fn row(&self, row_idx: usize) -> SheetResult<Cow<'_, [CellValue]>> {
+        self.rows
+            .get(row_idx)
+            .map(|row| Cow::Borrowed(row.as_slice()))
+            .ok_or_else(|| format!("Row {} not found", row_idx + 1).into())
+    }
+
+    fn cell_value(&self, row: u32, column: u32) -> SheetResult<Cow<'_, CellValue>> {
+        if row < 1 || column < 1 {
+            return Ok(Cow::Borrowed(CellValue::EMPTY));
+        }
+
+        let value = self
+            .rows
+            .get((row - 1) as usize)
+            .and_then(|cells| cells.get((column - 1) as usize))
+            .unwrap_or(CellValue::EMPTY);
+        Ok(Cow::Borrowed(value))
+    }
+}
+
+/// Iterator over the worksheets of an ODS spreadsheet
+struct OdsWorksheetIterator<'a> {
+    spreadsheet: &'a Spreadsheet,
+    index: usize,
+}
+
+impl<'a> WorksheetIterator<'a> for OdsWorksheetIterator<'a> {
+    fn next(&mut self) -> Option<SheetResult<Box<dyn Worksheet + 'a>>> {
+        if self.index >= self.spreadsheet.worksheet_count() {
+            return None;
+        }
+        let worksheet = self.spreadsheet.worksheet_by_index(self.index);
+        self.index += 1;
+        Some(worksheet)
+    }
+}
+
+impl WorkbookTrait for Spreadsheet {
+    fn active_worksheet(&self) -> SheetResult<Box<dyn Worksheet + '_>> {
+        self.worksheet_by_index(self.active_sheet_index())
+    }
+
+    fn worksheet_names(&self) -> &[String] {
+        self.grids()
+            .map(|grids| grids.names.as_slice())
+            .unwrap_or(&[])
+    }
+
+    fn worksheet_by_name(&self, name: &str) -> SheetResult<Box<dyn Worksheet + '_>> {
+        let grids = self.grids()?;
+        let index = grids
+            .names
+            .iter()
+            .position(|sheet| sheet == name)
+            .ok_or_else(|| {
+                crate::common::Error::InvalidReference(format!("Worksheet '{}' not found", name))
+            })?;
+        self.worksheet_by_index(index)
+    }
+
+    fn worksheet_by_index(&self, index: usize) -> SheetResult<Box<dyn Worksheet + '_>> {
+        let grids = self.grids()?;
+        match (grids.names.get(index), grids.rows.get(index)) {
+            (Some(name), Some(rows)) => Ok(Box::new(OdsWorksheet { name, rows })),
+            _ => Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
+                index
+            )))),
+        }
+    }
+
+    fn worksheets(&self) -> Box<dyn WorksheetIterator<'_> + '_> {
+        Box::new(OdsWorksheetIterator {
+            spreadsheet: self,
+            index: 0,
+        })
+    }
+
+    fn worksheet_count(&self) -> usize {
+        self.worksheet_names().len()
+    }
+
+    fn active_sheet_index(&self) -> usize {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::odf::SpreadsheetBuilder;
+
+    fn build_spreadsheet(cached_results: bool) -> Spreadsheet {
+        let mut builder = SpreadsheetBuilder::new();
+        builder.add_sheet("Data").unwrap();
+        builder.add_row_with_numbers(&[1.0, 2.0, 3.0]).unwrap();
+        builder
+            .set_cell_formula(1, 0, "of:=SUM([.A1:.C1])")
+            .unwrap();
+        builder
+            .set_cell_formula(1, 1, "of:=ROUND([.B1]/[.C1]; 2)")
+            .unwrap();
+        builder.add_sheet("My Summary").unwrap();
+        builder.set_cell_formula(0, 0, "of:=[$Data.A2]*2").unwrap();
+        builder
+            .set_cell_formula(0, 1, "of:=COM.MICROSOFT.IFS([Data.C1]>5;1;TRUE();2)")
+            .unwrap();
+        builder
+            .set_cell_formula(1, 0, "of:=[$'My Summary'.A1]+[.B1]")
+            .unwrap();
+
+        let bytes = builder.build().unwrap();
+        let bytes = if cached_results {
+            bytes
+        } else {
+            strip_cached_results(bytes)
+        };
+        Spreadsheet::from_bytes(bytes).unwrap()
+    }
+
+    /// Remove the placeholder results the builder writes for formula cells,
+    /// as if the file had been saved without recalculation
+    fn strip_cached_results(bytes: Vec<u8>) -> Vec<u8> {
+        use std::io::{Cursor, Read, Write};
+
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).unwrap();
+        let mut output = Vec::new();
+        {
+            let mut zip = zip::ZipWriter::new(Cursor::new(&mut output));
+            let options = zip::write::SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Stored);
+
+            for index in 0..archive.len() {
+                let mut file = archive.by_index(index).unwrap();
+                let name = file.name().to_string();
+                let mut data = Vec::new();
+                file.read_to_end(&mut data).unwrap();
+
+                if name == "content.xml" {
+                    data = String::from_utf8(data)
+                        .unwrap()
+                        .replace(
+                            r#" office:value-type="float" office:value="0"><text:p>0</text:p>"#,
+                            ">",
+                        )
+                        .into_bytes();
+                }
+                zip.start_file(name, options).unwrap();
+                zip.write_all(&data).unwrap();
+            }
+            zip.finish().unwrap();
+        }
+        output
+    }
+
+    #[test]
+    fn test_workbook_trait() {
+        let spreadsheet = build_spreadsheet(true);
+        assert_eq!(spreadsheet.worksheet_names(), ["Data", "My Summary"]);
+        assert_eq!(spreadsheet.worksheet_count(), 2);
+
+        let data = spreadsheet.worksheet_by_name("Data").unwrap();
+        assert_eq!(data.dimensions(), Some((1, 1, 2, 3)));
+        assert_eq!(*data.cell_value(1, 2).unwrap(), CellValue::Float(2.0));
+        match &*data.cell_value(2, 1).unwrap() {
+            CellValue::Formula {
+                formula,
+                cached_value,
+                ..
+            } => {
+                assert_eq!(formula, "SUM(A1:C1)");
+                assert_eq!(cached_value.as_deref(), Some(&CellValue::Float(0.0)));
+            },
+            other => panic!("Expected formula, got {:?}", other),
+        }
+        assert_eq!(
+            data.cell_by_coordinate("C1").unwrap().value(),
+            &CellValue::Float(3.0)
+        );
+
+        let mut names = Vec::new();
+        let mut worksheets = spreadsheet.worksheets();
+        while let Some(worksheet) = worksheets.next() {
+            names.push(worksheet.unwrap().name().to_string());
+        }
+        assert_eq!(names, ["Data", "My Summary"]);
+        assert!(spreadsheet.worksheet_by_name("Missing").is_err());
+    }
+
+    #[cfg(feature = "eval_engine")]
+    #[tokio::test]
+    async fn test_evaluate_openformula() {
+        use crate::sheet::FormulaEvaluator;
+
+        let spreadsheet = build_spreadsheet(false);
+        let evaluator = FormulaEvaluator::new(&spreadsheet);
+
+        let as_number = |value: CellValue| match value {
+            CellValue::Float(n) => n,
+            CellValue::Int(n) => n as f64,
+            other => panic!("Expected number, got {:?}", other),
+        };
+
+        let sum = evaluator.evaluate_cell("Data", 2, 1).await.unwrap();
+        assert_eq!(as_number(sum), 6.0);
+        let ratio = evaluator.evaluate_cell("Data", 2, 2).await.unwrap();
+        assert_eq!(as_number(ratio), 0.67);
+
+        let doubled = evaluator.evaluate_cell("My Summary", 1, 1).await.unwrap();
+        assert_eq!(as_number(doubled), 12.0);
+        let ifs = evaluator.evaluate_cell("My Summary", 1, 2).await.unwrap();
+        assert_eq!(as_number(ifs), 2.0);
+        let total = evaluator.evaluate_cell("My Summary", 2, 1).await.unwrap();
+        assert_eq!(as_number(total), 14.0);
+    }
+
+    #[test]
+    fn test_convert_dates_and_times() {
+        assert_eq!(
+            convert_value(&OdsValue::Date("2024-03-15".to_string())),
+            CellValue::DateTime(45366.0)
+        );
+        assert_eq!(
+            convert_value(&OdsValue::Date("2024-03-15T12:00:00".to_string())),
+            CellValue::DateTime(45366.5)
+        );
+        assert_eq!(
+            convert_value(&OdsValue::Time("PT12H30M00S".to_string())),
+            CellValue::DateTime(0.5208333333333334)
+        );
+        assert_eq!(
+            convert_value(&OdsValue::Time("soon".to_string())),
+            CellValue::String("soon".to_string())
+        );
+    }
+}
@@ -2,8 +2,8 @@
 //!
 //! This module provides a builder pattern for creating new ODT documents from scratch.
 
-use crate::common::{Metadata, Result, xml::escape_xml};
-use crate::odf::core::PackageWriter;
+use crate::common::{CustomPropertyValue, Metadata, Result};
+use crate::odf::core::{PackageWriter, meta_xml};
 use crate::odf::elements::table::Table;
 use crate::odf::elements::text::{Heading, List, ListItem, Paragraph, Span};
 use std::path::Path;
@@ -85,6 +85,43 @@ impl DocumentBuilder {
         self.metadata = metadata;
     }
 
+    /// Get a mutable reference to the document metadata
+    ///
+    /// Every field written to meta.xml can be set here, including the
+    /// language and the revision, which is written as the editing cycles.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Set a user-defined metadata field
+    ///
+    /// The field is written as a `meta:user-defined` element whose value type
+    /// (string, float, date or boolean) follows the value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - Name of the field
+    /// * `value` - Value of the field
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use litchi::odf::DocumentBuilder;
+    ///
+    /// let mut builder = DocumentBuilder::new();
+    /// builder.set_user_defined("DocumentId", "DOC-0042");
+    /// builder.set_user_defined("Reviewed", true);
+    /// ```
+    pub fn set_user_defined(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<CustomPropertyValue>,
+    ) {
+        self.metadata
+            .custom_properties
+            .insert(name.into(), value.into());
+    }
+
     /// Add a paragraph with text
     ///
     /// # Arguments
@@ -400,44 +437,7 @@ impl DocumentBuilder {
 
     /// Generate meta.xml with metadata
     fn generate_meta_xml(&self) -> String {
-        let now = chrono::Utc::now().to_rfc3339();
-
-        let mut meta = format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" office:version="1.3"><office:meta><meta:generator>Litchi/0.0.1</meta:generator><meta:creation-date>{}</meta:creation-date><dc:date>{}</dc:date>"#,
-            now, now
-        );
-
-        // Add optional metadata fields
-        if let Some(ref title) = self.metadata.title {
-            meta.push_str(&format!("<dc:title>{}</dc:title>", escape_xml(title)));
-        }
-
-        if let Some(ref author) = self.metadata.author {
-            meta.push_str(&format!("<dc:creator>{}</dc:creator>", escape_xml(author)));
-        }
-
-        if let Some(ref subject) = self.metadata.subject {
-            meta.push_str(&format!("<dc:subject>{}</dc:subject>", escape_xml(subject)));
-        }
-
-        if let Some(ref description) = self.metadata.description {
-            meta.push_str(&format!(
-                "<dc:description>{}</dc:description>",
-                escape_xml(description)
-            ));
-        }
-
-        if let Some(ref keywords) = self.metadata.keywords {
-            meta.push_str(&format!(
-                "<meta:keyword>{}</meta:keyword>",
-                escape_xml(keywords)
-            ));
-        }
-
-        meta.push_str("</office:meta>");
-        meta.push_str("</office:document-meta>");
-
-        meta
+        meta_xml(&self.metadata)
     }
 
     /// Generate styles.xml with list styles
@@ -781,6 +781,45 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_metadata_read_back() {
+        use crate::common::CustomPropertyValue;
+        use crate::odf::{Document, MutableDocument};
+
+        let mut builder = DocumentBuilder::new();
+        builder.metadata_mut().title = Some("Stamped".to_string());
+        builder.metadata_mut().language = Some("de-DE".to_string());
+        builder.metadata_mut().revision = Some("2".to_string());
+        builder.set_user_defined("DocumentId", "DOC-0042");
+        builder.set_user_defined("Weight", 0.75);
+        builder.add_paragraph("Body").unwrap();
+
+        let document = Document::from_bytes(builder.build().unwrap()).unwrap();
+        let metadata = document.metadata().unwrap();
+        assert_eq!(metadata.title, Some("Stamped".to_string()));
+        assert_eq!(metadata.language, Some("de-DE".to_string()));
+        assert_eq!(metadata.revision, Some("2".to_string()));
+        assert_eq!(
+            metadata.custom_properties.get("Weight"),
+            Some(&CustomPropertyValue::Float(0.75))
+        );
+
+        // Existing fields survive a mutable round trip alongside new ones
+        let mut mutable = MutableDocument::from_document(document).unwrap();
+        mutable.set_user_defined("Approved", true);
+        let reloaded = Document::from_bytes(mutable.to_bytes().unwrap()).unwrap();
+        let metadata = reloaded.metadata().unwrap();
+        assert_eq!(metadata.language, Some("de-DE".to_string()));
+        assert_eq!(
+            metadata.custom_properties.get("DocumentId"),
+            Some(&CustomPropertyValue::String("DOC-0042".to_string()))
+        );
+        assert_eq!(
+            metadata.custom_properties.get("Approved"),
+            Some(&CustomPropertyValue::Bool(true))
+        );
+    }
+
     #[test]
     fn test_empty_document_build() {
         let builder = DocumentBuilder::new();
@@ -1,11 +1,12 @@
 //! OpenDocument Text document structure and API.
 
-use crate::common::{Error, Metadata, Result};
+use crate::common::{Error, Language, Metadata, Protection, Result};
 use crate::odf::core::{Content, Meta, OwnedPackage, Styles};
-use crate::odf::elements::style::{StyleElements, StyleRegistry};
+use crate::odf::elements::style::{StyleElements, StyleLanguages, StyleRegistry};
 use crate::odf::elements::table::Table as ElementTable;
 use crate::odf::elements::text::{Paragraph as ElementParagraph, TextElements};
 use std::path::Path;
+use std::sync::{Arc, OnceLock};
 
 /// An OpenDocument text document (.odt).
 ///
@@ -52,6 +53,8 @@ pub struct Document {
     meta: Option<Meta>,
     /// Registry of all styles in the document
     style_registry: StyleRegistry,
+    /// Resolved style languages, read on first use
+    style_languages: OnceLock<Arc<StyleLanguages>>,
 }
 
 impl Document {
@@ -112,7 +115,49 @@ impl Document {
     /// # }
     /// ```
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
-        let owned_package = OwnedPackage::from_bytes(bytes)?;
+        Self::from_package(OwnedPackage::from_bytes(bytes)?)
+    }
+
+    /// Open a password-protected ODT document.
+    ///
+    /// Entries encrypted with Blowfish CFB or AES-CBC, as written by
+    /// OpenOffice.org and LibreOffice, are decrypted in memory. Files that are
+    /// not encrypted open normally and the password is ignored.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::IncorrectPassword`] if the password does not decrypt the file
+    /// - [`Error::UnsupportedEncryption`] for other encryption schemes, such as
+    ///   the Argon2id and AES-GCM encryption of LibreOffice 24.2 and later
+    /// - [`Error::CorruptedFile`] if an encrypted entry is damaged
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use litchi::odf::Document;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let doc = Document::open_with_password("protected.odt", "secret")?;
+    /// println!("{}", doc.text()?);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "odf_encryption")]
+    pub fn open_with_password<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        let bytes = std::fs::read(path.as_ref())?;
+        Self::from_bytes_with_password(bytes, password)
+    }
+
+    /// Create a Document from the bytes of a possibly password-protected file.
+    ///
+    /// See [`Document::open_with_password`] for details.
+    #[cfg(feature = "odf_encryption")]
+    pub fn from_bytes_with_password(bytes: Vec<u8>, password: &str) -> Result<Self> {
+        Self::from_package(OwnedPackage::from_bytes_with_password(bytes, password)?)
+    }
+
+    /// Parse the parts of an opened package.
+    fn from_package(owned_package: OwnedPackage) -> Result<Self> {
         let package = owned_package.package()?;
 
         // Verify this is a text document
@@ -166,6 +211,7 @@ impl Document {
             styles,
             meta,
             style_registry,
+            style_languages: OnceLock::new(),
         })
     }
 
@@ -243,7 +289,62 @@ impl Document {
     /// # }
     /// ```
     pub fn paragraphs(&self) -> Result<Vec<ElementParagraph>> {
-        TextElements::parse_paragraphs(self.content.xml_content())
+        let mut paragraphs = TextElements::parse_paragraphs(self.content.xml_content())?;
+        self.apply_style_languages(paragraphs.iter_mut());
+        Ok(paragraphs)
+    }
+
+    /// Get the document default languages as BCP-47 tags.
+    ///
+    /// These are the languages of the default paragraph style
+    /// (`style:default-style`), which apply to text whose styles do not
+    /// declare their own.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use litchi::odf::Document;
+    ///
+    /// # fn main() -> litchi::Result<()> {
+    /// let doc = Document::open("document.odt")?;
+    /// if let Some(tag) = doc.default_language().latin {
+    ///     println!("Written in {}", tag);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn default_language(&self) -> Language {
+        self.style_languages().defaults().clone()
+    }
+
+    /// Get the languages of the styles, reading them on first use.
+    fn style_languages(&self) -> &Arc<StyleLanguages> {
+        self.style_languages.get_or_init(|| {
+            let styles = self
+                .styles
+                .as_ref()
+                .map_or("", |styles| styles.xml_content());
+            Arc::new(StyleLanguages::from_xml(&[
+                styles,
+                self.content.xml_content(),
+            ]))
+        })
+    }
+
+    /// Attach the style languages to paragraphs so that their spans resolve
+    /// languages through styles. Nothing is attached when no style declares
+    /// a language.
+    fn apply_style_languages<'p>(
+        &self,
+        paragraphs: impl Iterator<Item = &'p mut ElementParagraph>,
+    ) {
+        let languages = self.style_languages();
+        if languages.is_empty() {
+            return;
+        }
+        for para in paragraphs {
+            para.set_style_languages(Arc::clone(languages));
+        }
     }
 
     /// Get all tables in the document.
@@ -311,10 +412,15 @@ impl Document {
     /// # }
     /// ```
     pub fn elements(&self) -> Result<Vec<crate::odf::elements::parser::DocumentOrderElement>> {
-        use crate::odf::elements::parser::DocumentParser;
+        use crate::odf::elements::parser::{DocumentOrderElement, DocumentParser};
 
         // Parse all elements in document order using the generic ODF parser
-        DocumentParser::parse_elements_in_order(self.content.xml_content())
+        let mut elements = DocumentParser::parse_elements_in_order(self.content.xml_content())?;
+        self.apply_style_languages(elements.iter_mut().filter_map(|element| match element {
+            DocumentOrderElement::Paragraph(para) => Some(para),
+            _ => None,
+        }));
+        Ok(elements)
     }
 
     /// Get document metadata.
@@ -356,6 +462,18 @@ impl Document {
         }
     }
 
+    /// Get the editing protection of the document.
+    ///
+    /// Reads the `LoadReadonly` recommendation and the change recording and
+    /// modify passwords from settings.xml.
+    pub fn protection(&self) -> Result<Protection> {
+        Ok(self
+            .package
+            .settings()?
+            .map(|settings| settings.protection())
+            .unwrap_or_default())
+    }
+
     /// Get the style registry for this document.
     ///
     /// The style registry contains all styles defined in the document,
@@ -751,7 +869,14 @@ impl Document {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(Error::Other(format!("XML parse error: {}", e))),
+                Err(e) => {
+                    return Err(Error::xml_at(
+                        Some("content.xml"),
+                        content_xml.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
+                },
                 _ => {},
             }
             buf.clear();
@@ -808,7 +933,14 @@ impl Document {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(Error::Other(format!("XML parse error: {}", e))),
+                Err(e) => {
+                    return Err(Error::xml_at(
+                        Some("content.xml"),
+                        content_xml.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
+                },
                 _ => {},
             }
             buf.clear();
@@ -862,7 +994,14 @@ impl Document {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(Error::Other(format!("XML parse error: {}", e))),
+                Err(e) => {
+                    return Err(Error::xml_at(
+                        Some("content.xml"),
+                        content_xml.as_bytes(),
+                        reader.error_position(),
+                        e,
+                    ));
+                },
                 _ => {},
             }
             buf.clear();
@@ -29,7 +29,7 @@
 //! - ✅ `add_table()` - Add tables with rows/cells
 //! - ✅ `add_list()` - Add lists
 //! - ✅ `add_heading()` - Add headings with levels
-//! - ✅ `set_title()` / `set_author()` - Set metadata
+//! - ✅ `set_metadata()` / `metadata_mut()` / `set_user_defined()` - Set metadata
 //! - ✅ `save()` / `to_bytes()` - Write to file or bytes
 //! - ✅ `MutableDocument` - Modify existing documents
 //!
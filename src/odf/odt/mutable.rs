@@ -3,8 +3,8 @@
 //! This module provides a mutable wrapper around ODT documents that allows
 //! for in-place modification of content, styles, and metadata.
 
-use crate::common::{Metadata, Result, xml::escape_xml};
-use crate::odf::core::{OdfStructure, PackageWriter};
+use crate::common::{CustomPropertyValue, Metadata, Result};
+use crate::odf::core::{OdfStructure, PackageWriter, meta_xml};
 use crate::odf::elements::table::Table;
 use crate::odf::elements::text::Paragraph;
 use crate::odf::odt::Document;
@@ -195,6 +195,20 @@ impl MutableDocument {
         &mut self.metadata
     }
 
+    /// Set a user-defined metadata field.
+    ///
+    /// The field is written as a `meta:user-defined` element whose value type
+    /// (string, float, date or boolean) follows the value.
+    pub fn set_user_defined(
+        &mut self,
+        name: impl Into<String>,
+        value: impl Into<CustomPropertyValue>,
+    ) {
+        self.metadata
+            .custom_properties
+            .insert(name.into(), value.into());
+    }
+
     /// Add a new paragraph to the end of the document.
     ///
     /// # Arguments
@@ -493,71 +507,7 @@ impl MutableDocument {
 
     /// Generate meta.xml with current metadata.
     fn generate_meta_xml(&self) -> String {
-        let now = chrono::Utc::now().to_rfc3339();
-        let mut estimated = 64usize;
-        estimated += self.metadata.title.as_ref().map(|s| s.len()).unwrap_or(0);
-        estimated += self.metadata.author.as_ref().map(|s| s.len()).unwrap_or(0);
-        estimated += self.metadata.subject.as_ref().map(|s| s.len()).unwrap_or(0);
-        estimated += self
-            .metadata
-            .description
-            .as_ref()
-            .map(|s| s.len())
-            .unwrap_or(0);
-        estimated += self
-            .metadata
-            .keywords
-            .as_ref()
-            .map(|s| s.len())
-            .unwrap_or(0);
-        let mut meta_fields = String::with_capacity(estimated);
-
-        // Add optional metadata fields
-        if let Some(ref title) = self.metadata.title {
-            let escaped_title = escape_xml(title);
-            meta_fields.push_str(&xml_minifier::minified_xml_format!(
-                r#"<dc:title>{}</dc:title>"#,
-                escaped_title
-            ));
-        }
-
-        if let Some(ref author) = self.metadata.author {
-            let escaped_author = escape_xml(author);
-            meta_fields.push_str(&xml_minifier::minified_xml_format!(
-                r#"<dc:creator>{}</dc:creator>"#,
-                escaped_author
-            ));
-        }
-
-        if let Some(ref subject) = self.metadata.subject {
-            let escaped_subject = escape_xml(subject);
-            meta_fields.push_str(&xml_minifier::minified_xml_format!(
-                r#"<dc:subject>{}</dc:subject>"#,
-                escaped_subject
-            ));
-        }
-
-        if let Some(ref description) = self.metadata.description {
-            let escaped_description = escape_xml(description);
-            meta_fields.push_str(&xml_minifier::minified_xml_format!(
-                r#"<dc:description>{}</dc:description>"#,
-                escaped_description
-            ));
-        }
-
-        if let Some(ref keywords) = self.metadata.keywords {
-            let escaped_keywords = escape_xml(keywords);
-            meta_fields.push_str(&xml_minifier::minified_xml_format!(
-                r#"<meta:keyword>{}</meta:keyword>"#,
-                escaped_keywords
-            ));
-        }
-
-        xml_minifier::minified_xml_format!(
-            r#"<?xml version="1.0" encoding="UTF-8"?><office:document-meta xmlns:office="urn:oasis:names:tc:opendocument:xmlns:office:1.0" xmlns:xlink="http://www.w3.org/1999/xlink" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:meta="urn:oasis:names:tc:opendocument:xmlns:meta:1.0" office:version="1.3"><office:meta><meta:generator>Litchi/0.0.1</meta:generator><dc:date>{}</dc:date>{}</office:meta></office:document-meta>"#,
-            now,
-            meta_fields
-        )
+        meta_xml(&self.metadata)
     }
 
     /// Save the modified document to a file.
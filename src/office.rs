@@ -0,0 +1,346 @@
+//! Format-agnostic entry point for arbitrary office files.
+//!
+//! [`open`] and [`open_from_bytes`] detect the format once and hand the parsed
+//! structure to the matching unified API, so callers that ingest mixed files do
+//! not have to branch on [`FileFormat`](crate::FileFormat) themselves.
+//!
+//! ```rust,no_run
+//! use litchi::{OfficeFile, TextExtract};
+//!
+//! let file = litchi::open("upload.bin")?;
+//! let kind = match &file {
+//!     OfficeFile::Document(_) => "document",
+//!     OfficeFile::Presentation(_) => "presentation",
+//!     OfficeFile::Spreadsheet(_) => "spreadsheet",
+//! };
+//! println!("{kind}: {}", file.text()?);
+//! # Ok::<(), litchi::Error>(())
+//! ```
+
+use crate::common::detection::DetectedFormat;
+use crate::common::detection::detected::detect_format_checked;
+use crate::common::{Error, Metadata, Result};
+use std::path::Path;
+
+#[cfg(any(
+    feature = "ole",
+    feature = "ooxml",
+    feature = "rtf",
+    feature = "odf",
+    feature = "iwa"
+))]
+use crate::Document;
+#[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+use crate::{Presentation, sheet::Workbook};
+
+/// Uniform access to the text and metadata of any opened file.
+pub trait TextExtract {
+    /// Extract all text content.
+    fn text(&self) -> Result<String>;
+
+    /// Document properties such as title, author and dates.
+    fn metadata(&self) -> Result<Metadata>;
+}
+
+/// An opened office file of any supported format.
+///
+/// | Variant | Formats |
+/// |---------|---------|
+/// | `Document` | .doc, .docx, .rtf, .odt, .pages |
+/// | `Presentation` | .ppt, .pptx, .odp, .key |
+/// | `Spreadsheet` | .xls, .xlsx, .xlsb, .ods, .numbers |
+///
+/// Which formats are available depends on the enabled features.
+#[allow(clippy::large_enum_variant)]
+pub enum OfficeFile {
+    /// A word-processing document
+    #[cfg(any(
+        feature = "ole",
+        feature = "ooxml",
+        feature = "rtf",
+        feature = "odf",
+        feature = "iwa"
+    ))]
+    Document(Document),
+    /// A slide presentation
+    #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+    Presentation(Presentation),
+    /// A spreadsheet workbook
+    #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+    Spreadsheet(Workbook),
+}
+
+impl TextExtract for OfficeFile {
+    fn text(&self) -> Result<String> {
+        match self {
+            #[cfg(any(
+                feature = "ole",
+                feature = "ooxml",
+                feature = "rtf",
+                feature = "odf",
+                feature = "iwa"
+            ))]
+            OfficeFile::Document(doc) => TextExtract::text(doc),
+            #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+            OfficeFile::Presentation(pres) => TextExtract::text(pres),
+            #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+            OfficeFile::Spreadsheet(workbook) => TextExtract::text(workbook),
+        }
+    }
+
+    fn metadata(&self) -> Result<Metadata> {
+        match self {
+            #[cfg(any(
+                feature = "ole",
+                feature = "ooxml",
+                feature = "rtf",
+                feature = "odf",
+                feature = "iwa"
+            ))]
+            OfficeFile::Document(doc) => TextExtract::metadata(doc),
+            #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+            OfficeFile::Presentation(pres) => TextExtract::metadata(pres),
+            #[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+            OfficeFile::Spreadsheet(workbook) => TextExtract::metadata(workbook),
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "ole",
+    feature = "ooxml",
+    feature = "rtf",
+    feature = "odf",
+    feature = "iwa"
+))]
+impl TextExtract for Document {
+    fn text(&self) -> Result<String> {
+        Document::text(self)
+    }
+
+    fn metadata(&self) -> Result<Metadata> {
+        Document::metadata(self)
+    }
+}
+
+#[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+impl TextExtract for Presentation {
+    fn text(&self) -> Result<String> {
+        Presentation::text(self)
+    }
+
+    fn metadata(&self) -> Result<Metadata> {
+        Ok(Presentation::metadata(self)?.unwrap_or_default())
+    }
+}
+
+#[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+impl TextExtract for Workbook {
+    fn text(&self) -> Result<String> {
+        Workbook::text(self).map_err(sheet_error)
+    }
+
+    fn metadata(&self) -> Result<Metadata> {
+        Workbook::metadata(self).map_err(sheet_error)
+    }
+}
+
+/// Open any supported office file from a path.
+///
+/// The format is detected from the file content, not the extension.
+///
+/// # Errors
+///
+/// - [`Error::NotOfficeFile`] if the format is unknown or its feature is disabled
+/// - [`Error::Encrypted`] if the file is password-protected
+pub fn open<P: AsRef<Path>>(path: P) -> Result<OfficeFile> {
+    open_from_bytes(std::fs::read(path)?)
+}
+
+/// Open any supported office file from memory.
+///
+/// See [`open`] for details.
+pub fn open_from_bytes(bytes: Vec<u8>) -> Result<OfficeFile> {
+    from_detected(detect_format_checked(bytes)?)
+}
+
+/// Open any supported office file that may be password-protected.
+///
+/// Encrypted OOXML packages are decrypted in memory; other files open as with
+/// [`open`] and the password is ignored.
+///
+/// # Errors
+///
+/// - [`Error::IncorrectPassword`] if the password does not unlock the file
+/// - [`Error::UnsupportedEncryption`] if the encryption scheme is not supported
+/// - [`Error::Encrypted`] for password-protected legacy .doc, .xls and .ppt files
+#[cfg(feature = "ooxml_encryption")]
+pub fn open_with_password<P: AsRef<Path>>(path: P, password: &str) -> Result<OfficeFile> {
+    open_from_bytes_with_password(std::fs::read(path)?, password)
+}
+
+/// Open any supported office file that may be password-protected from memory.
+///
+/// See [`open_with_password`] for details.
+#[cfg(feature = "ooxml_encryption")]
+pub fn open_from_bytes_with_password(bytes: Vec<u8>, password: &str) -> Result<OfficeFile> {
+    use crate::common::detection::detect_format_with_password;
+
+    from_detected(detect_format_with_password(bytes, password)?)
+}
+
+fn from_detected(detected: DetectedFormat) -> Result<OfficeFile> {
+    match detected {
+        #[cfg(feature = "ole")]
+        DetectedFormat::Encrypted(_) => Err(Error::Encrypted(
+            "password-protected OOXML package".to_string(),
+        )),
+
+        #[cfg(feature = "ole")]
+        detected @ DetectedFormat::Doc(_) => {
+            Ok(OfficeFile::Document(Document::from_detected(detected)?))
+        },
+        #[cfg(feature = "ooxml")]
+        detected @ DetectedFormat::Docx(_) => {
+            Ok(OfficeFile::Document(Document::from_detected(detected)?))
+        },
+        #[cfg(feature = "rtf")]
+        detected @ DetectedFormat::Rtf(_) => {
+            Ok(OfficeFile::Document(Document::from_detected(detected)?))
+        },
+        #[cfg(feature = "odf")]
+        detected @ DetectedFormat::Odt(_) => {
+            Ok(OfficeFile::Document(Document::from_detected(detected)?))
+        },
+        #[cfg(feature = "iwa")]
+        detected @ DetectedFormat::Pages(_) => {
+            Ok(OfficeFile::Document(Document::from_detected(detected)?))
+        },
+
+        #[cfg(feature = "ole")]
+        detected @ DetectedFormat::Ppt(_) => Ok(OfficeFile::Presentation(
+            Presentation::from_detected(detected)?,
+        )),
+        #[cfg(feature = "ooxml")]
+        detected @ DetectedFormat::Pptx(_) => Ok(OfficeFile::Presentation(
+            Presentation::from_detected(detected)?,
+        )),
+        #[cfg(feature = "odf")]
+        detected @ DetectedFormat::Odp(_) => Ok(OfficeFile::Presentation(
+            Presentation::from_detected(detected)?,
+        )),
+        #[cfg(feature = "iwa")]
+        detected @ DetectedFormat::Keynote(_) => Ok(OfficeFile::Presentation(
+            Presentation::from_detected(detected)?,
+        )),
+
+        #[cfg(feature = "ole")]
+        detected @ DetectedFormat::Xls(_) => Ok(OfficeFile::Spreadsheet(
+            Workbook::from_detected(detected).map_err(sheet_error)?,
+        )),
+        #[cfg(feature = "ooxml")]
+        detected @ (DetectedFormat::Xlsx(_) | DetectedFormat::Xlsb(_)) => Ok(
+            OfficeFile::Spreadsheet(Workbook::from_detected(detected).map_err(sheet_error)?),
+        ),
+        #[cfg(feature = "odf")]
+        detected @ DetectedFormat::Ods(_) => Ok(OfficeFile::Spreadsheet(
+            Workbook::from_detected(detected).map_err(sheet_error)?,
+        )),
+        #[cfg(feature = "iwa")]
+        detected @ DetectedFormat::Numbers(_) => Ok(OfficeFile::Spreadsheet(
+            Workbook::from_detected(detected).map_err(sheet_error)?,
+        )),
+    }
+}
+
+/// Convert a boxed sheet error into the crate error, keeping typed errors.
+#[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+fn sheet_error(err: Box<dyn std::error::Error + Send + Sync>) -> Error {
+    match err.downcast::<Error>() {
+        Ok(err) => *err,
+        Err(err) => Error::Other(err.to_string()),
+    }
+}
+
+#[cfg(all(test, feature = "ole", feature = "ooxml"))]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_data(path: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data")
+            .join(path)
+    }
+
+    #[test]
+    fn opens_each_family() {
+        let doc = open(test_data("ooxml/docx/documentProperties.docx")).unwrap();
+        assert!(matches!(doc, OfficeFile::Document(_)));
+        assert_eq!(
+            doc.metadata().unwrap().title.as_deref(),
+            Some("Hello World")
+        );
+
+        let pres = open(test_data("ole/ppt/SampleShow.ppt")).unwrap();
+        assert!(matches!(pres, OfficeFile::Presentation(_)));
+        assert!(!pres.text().unwrap().is_empty());
+
+        let bytes =
+            std::fs::read(test_data("ooxml/xlsx/sheetProtection_not_protected.xlsx")).unwrap();
+        let workbook = open_from_bytes(bytes).unwrap();
+        assert!(matches!(workbook, OfficeFile::Spreadsheet(_)));
+        workbook.text().unwrap();
+    }
+
+    #[test]
+    fn rejects_encrypted_and_unknown_files() {
+        assert!(matches!(
+            open(test_data("ole/xls/password.xls")),
+            Err(Error::Encrypted(_))
+        ));
+        assert!(matches!(
+            open(test_data("ole/doc/PasswordProtected.doc")),
+            Err(Error::Encrypted(_))
+        ));
+        assert!(matches!(
+            open_from_bytes(b"plain text, not an office file".to_vec()),
+            Err(Error::NotOfficeFile)
+        ));
+    }
+
+    #[cfg(feature = "ooxml_encryption")]
+    #[test]
+    fn opens_password_protected_packages() {
+        use crate::ooxml::crypto::{
+            encrypt_ooxml_package_agile, encrypt_ooxml_package_standard_2007,
+        };
+
+        let docx = std::fs::read(test_data("ooxml/docx/documentProperties.docx")).unwrap();
+        let encrypted = encrypt_ooxml_package_agile(&docx, "secret").unwrap();
+
+        let doc = open_from_bytes_with_password(encrypted.clone(), "secret").unwrap();
+        assert_eq!(
+            doc.metadata().unwrap().title.as_deref(),
+            Some("Hello World")
+        );
+        assert!(matches!(
+            crate::Document::from_bytes_with_password(encrypted.clone(), "wrong"),
+            Err(Error::IncorrectPassword(_))
+        ));
+        assert!(matches!(
+            open_from_bytes(encrypted),
+            Err(Error::Encrypted(_))
+        ));
+
+        let xlsx =
+            std::fs::read(test_data("ooxml/xlsx/sheetProtection_not_protected.xlsx")).unwrap();
+        let encrypted = encrypt_ooxml_package_standard_2007(&xlsx, "pass").unwrap();
+        Workbook::from_bytes_with_password(encrypted, "pass").unwrap();
+
+        assert!(matches!(
+            open_with_password(test_data("ole/xls/password.xls"), "password"),
+            Err(Error::Encrypted(_))
+        ));
+    }
+}
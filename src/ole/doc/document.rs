@@ -6,15 +6,28 @@ use super::hyperlink::Hyperlink;
 use super::package::{DocError, Result};
 use super::paragraph::{Paragraph, Run};
 use super::parts::chp_bin_table::ChpBinTable;
+use super::parts::dop::DocumentProperties;
 use super::parts::fib::FileInformationBlock;
 use super::parts::fields::FieldsTable;
+use super::parts::font_table::FontTable;
 use super::parts::footnotes::{EndnotesTable, FootnotesTable};
 use super::parts::headers::HeadersTable;
 use super::parts::hyperlinks::HyperlinksTable;
 use super::parts::numbering::ListTables;
-use super::parts::paragraph_extractor::{ExtractedParagraph, ParagraphExtractor};
+use super::parts::pap_bin_table::PapBinTable;
+use super::parts::paragraph_extractor::{
+    ExtractedParagraph, ParagraphExtractor, is_paragraph_mark,
+};
+use super::parts::piece_table::PieceTable;
+use super::parts::sections::{SectionProperties, SectionTable};
+use super::parts::stylesheet::StyleSheet;
+use super::parts::tap::{CellProperties, TableProperties};
 use super::parts::text::TextExtractor;
+use super::parts::textboxes::TextBoxesTable;
 use super::table::Table;
+use super::text_box::TextBox;
+use crate::common::{Language, Protection, ProtectionKind};
+use crate::document::ListCounter;
 #[cfg(feature = "formula")]
 use crate::ole::mtef_extractor::MtefExtractor;
 use std::collections::HashMap;
@@ -67,6 +80,8 @@ pub struct Document {
     text_extractor: TextExtractor,
     /// Character property bin table - parsed once and shared across all paragraph extractors
     chp_bin_table: Option<ChpBinTable>,
+    /// Paragraph property bin table - parsed once and shared across all paragraph extractors
+    pap_bin_table: Option<PapBinTable>,
     /// Fields table - contains field information (embedded equations, hyperlinks, etc.)
     /// Used during initialization for hyperlink extraction; exposed via `fields_table()` accessor.
     fields_table: Option<FieldsTable>,
@@ -76,10 +91,16 @@ pub struct Document {
     footnotes_table: Option<FootnotesTable>,
     /// Endnotes table
     endnotes_table: Option<EndnotesTable>,
+    /// Text boxes table
+    text_boxes_table: Option<TextBoxesTable>,
     /// Hyperlinks table
     hyperlinks_table: Option<HyperlinksTable>,
     /// List/numbering tables
     list_tables: Option<ListTables>,
+    /// Embedded OLE objects (ObjectPool storage name -> standalone compound file)
+    object_pool: Vec<(String, Vec<u8>)>,
+    /// The `Macros` storage (VBA project) copied into a standalone compound file
+    vba_project_data: Option<Vec<u8>>,
     /// Extracted MTEF data from OLE streams (stream_name -> mtef_data)
     #[allow(dead_code)] // Stored for debugging and raw access
     mtef_data: std::collections::HashMap<String, Vec<u8>>,
@@ -131,8 +152,20 @@ impl Document {
         // According to Apache POI, pictures are stored in Data stream, not WordDocument stream
         let data_stream = ole.open_stream(&["Data"]).ok();
 
-        // Create text extractor
-        let text_extractor = TextExtractor::new(&fib, &word_document, &table_stream)?;
+        // Parse ChpBinTable once here to avoid re-parsing for each subdocument
+        // This is a major performance optimization since ChpBinTable::parse is expensive
+        let chp_bin_table = Self::parse_chp_bin_table(&fib, &table_stream, &word_document)?;
+        let pap_bin_table = Self::parse_pap_bin_table(&fib, &table_stream, &word_document);
+
+        // Create text extractor, decoding 8-bit text with the fonts of its runs
+        let font_table = FontTable::parse(&fib, &table_stream);
+        let text_extractor = TextExtractor::with_fonts(
+            &fib,
+            &word_document,
+            &table_stream,
+            chp_bin_table.as_ref(),
+            font_table.as_ref(),
+        )?;
 
         // Parse fields table to identify embedded equations and hyperlinks
         let fields_table = FieldsTable::parse(&fib, &table_stream).ok();
@@ -144,6 +177,9 @@ impl Document {
         let footnotes_table = FootnotesTable::parse(&fib, &table_stream).ok();
         let endnotes_table = EndnotesTable::parse(&fib, &table_stream).ok();
 
+        // Parse text boxes anchored in the main document
+        let text_boxes_table = TextBoxesTable::parse(&fib, &table_stream).ok();
+
         // Parse hyperlinks from fields table
         let hyperlinks_table = fields_table.as_ref().and_then(|ft| {
             HyperlinksTable::from_fields(ft, |start, end| {
@@ -155,6 +191,15 @@ impl Document {
         // Parse list/numbering tables
         let list_tables = ListTables::parse(&fib, &table_stream).ok();
 
+        // Copy the embedded OLE objects out of the ObjectPool storage
+        let object_pool = Self::extract_object_pool(ole);
+
+        // Copy the VBA project out of the Macros storage
+        let vba_project_data = ole
+            .directory_exists(&["Macros"])
+            .then(|| crate::ole::object::extract_storage(ole, &["Macros"]).ok())
+            .flatten();
+
         // Extract MTEF data from OLE streams
         let mtef_data = Self::extract_mtef_data(ole)?;
 
@@ -164,10 +209,6 @@ impl Document {
         #[cfg(not(feature = "formula"))]
         let parsed_mtef = Self::parse_all_mtef_data(&mtef_data)?;
 
-        // Parse ChpBinTable once here to avoid re-parsing for each subdocument
-        // This is a major performance optimization since ChpBinTable::parse is expensive
-        let chp_bin_table = Self::parse_chp_bin_table(&fib, &table_stream, &word_document)?;
-
         Ok(Self {
             fib,
             word_document,
@@ -175,12 +216,16 @@ impl Document {
             table_stream,
             text_extractor,
             chp_bin_table,
+            pap_bin_table,
             fields_table,
             headers_table,
             footnotes_table,
             endnotes_table,
+            text_boxes_table,
             hyperlinks_table,
             list_tables,
+            object_pool,
+            vba_project_data,
             mtef_data,
             #[cfg(feature = "formula")]
             formula_arenas,
@@ -190,6 +235,28 @@ impl Document {
         })
     }
 
+    /// Copy each object storage below ObjectPool into a standalone compound file.
+    ///
+    /// Storages that can't be copied are skipped.
+    fn extract_object_pool<R: Read + Seek>(ole: &mut OleFile<R>) -> Vec<(String, Vec<u8>)> {
+        let Ok(entries) = ole.list_directory_entries(&["ObjectPool"]) else {
+            return Vec::new();
+        };
+        let names: Vec<String> = entries
+            .iter()
+            .filter(|entry| entry.entry_type == 1 && entry.name.starts_with('_'))
+            .map(|entry| entry.name.clone())
+            .collect();
+
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let data = crate::ole::object::extract_storage(ole, &["ObjectPool", &name]).ok()?;
+                Some((name, data))
+            })
+            .collect()
+    }
+
     /// Extract MTEF data from OLE streams during document initialization
     ///
     /// This method extracts embedded equation objects from the ObjectPool directory.
@@ -230,22 +297,8 @@ impl Document {
         table_stream: &[u8],
         word_document: &[u8],
     ) -> Result<Option<ChpBinTable>> {
-        use super::parts::piece_table::PieceTable;
-
         // Parse piece table (required for FC-to-CP conversion in ChpBinTable)
-        // According to [MS-DOC], fcClx is at FIB offset 0x01A2
-        // In FibRgFcLcb97 (starting at FIB offset 154), this is index 33
-        let piece_table = if let Some((offset, length)) = fib.get_table_pointer(33) {
-            if length > 0 && (offset as usize) < table_stream.len() {
-                let clx_data = &table_stream[offset as usize..];
-                let clx_len = length.min((table_stream.len() - offset as usize) as u32) as usize;
-                PieceTable::parse(&clx_data[..clx_len])
-            } else {
-                None
-            }
-        } else {
-            None
-        };
+        let piece_table = Self::parse_piece_table(fib, table_stream);
 
         // Parse ChpBinTable if we have a piece table
         // Index 12 in FibRgFcLcb97 is fcPlcfBteChpx/lcbPlcfBteChpx (PLCFBTECHPX)
@@ -272,6 +325,20 @@ impl Document {
         Ok(chp_bin_table)
     }
 
+    /// Parse the piece table used to map file positions (FC) to character positions (CP).
+    fn parse_piece_table(fib: &FileInformationBlock, table_stream: &[u8]) -> Option<PieceTable> {
+        // According to [MS-DOC], fcClx is at FIB offset 0x01A2
+        // In FibRgFcLcb97 (starting at FIB offset 154), this is index 33
+        let (offset, length) = fib.get_table_pointer(33)?;
+        if length > 0 && (offset as usize) < table_stream.len() {
+            let clx_data = &table_stream[offset as usize..];
+            let clx_len = length.min((table_stream.len() - offset as usize) as u32) as usize;
+            PieceTable::parse(&clx_data[..clx_len])
+        } else {
+            None
+        }
+    }
+
     /// Parse all extracted MTEF data into AST nodes using proper arena allocation.
     ///
     /// This function creates Formula arenas for each MTEF stream and stores them
@@ -529,7 +596,8 @@ impl Document {
     ///
     /// Each section can have up to six stories: first-page header/footer,
     /// even-page header/footer, and odd-page (default) header/footer.
-    /// Empty stories (where start_cp == end_cp) are omitted.
+    /// Empty stories (where start_cp == end_cp) are omitted; a section
+    /// without a story of some type continues that of the previous section.
     ///
     /// # Example
     ///
@@ -559,6 +627,7 @@ impl Document {
             let paragraphs = self.extract_paragraphs_for_range(story.start_cp, story.end_cp)?;
 
             let mut hf = HeaderFooter::new(story.story_type, text);
+            hf.section_index = story.section_index;
             hf.paragraphs = paragraphs;
             result.push(hf);
         }
@@ -660,6 +729,156 @@ impl Document {
         Ok(result)
     }
 
+    /// Get all text boxes anchored in the main document.
+    ///
+    /// Text boxes are Escher shapes whose text is kept in the text box
+    /// subdocument; they are returned in anchor order and their text is not
+    /// part of [`text`](Self::text). Text boxes in headers and footers are
+    /// not included.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// for text_box in doc.text_boxes()? {
+    ///     let index = doc.paragraph_index_at(text_box.anchor_position);
+    ///     println!("Text box in paragraph {}: {}", index, text_box.text());
+    /// }
+    /// ```
+    pub fn text_boxes(&self) -> Result<Vec<TextBox>> {
+        let table = match &self.text_boxes_table {
+            Some(t) => t,
+            None => return Ok(Vec::new()),
+        };
+
+        let mut result = Vec::with_capacity(table.count());
+        for reference in table.references() {
+            let paragraphs =
+                self.extract_paragraphs_for_range(reference.text_start_cp, reference.text_end_cp)?;
+            let text = paragraphs
+                .iter()
+                .map(|para| {
+                    para.text()
+                        .map(|text| text.trim_end_matches('\r').to_string())
+                })
+                .collect::<Result<Vec<_>>>()?
+                .join("\n");
+
+            let mut text_box = TextBox::new(reference.anchor_cp, reference.shape_id, text);
+            text_box.paragraphs = paragraphs;
+            result.push(text_box);
+        }
+
+        Ok(result)
+    }
+
+    /// Get the index of the main-document paragraph containing a character position.
+    ///
+    /// Paragraphs end with a paragraph or cell mark, so this counts the marks
+    /// before `cp`. The result indexes the main-document paragraphs in reading
+    /// order, which is useful to anchor footnote and endnote references.
+    pub fn paragraph_index_at(&self, cp: u32) -> usize {
+        self.text_extractor
+            .text_at_range(0, cp)
+            .chars()
+            .filter(|&c| is_paragraph_mark(c))
+            .count()
+    }
+
+    /// Get the outline level of every heading paragraph.
+    ///
+    /// Returns `(paragraph index, outline level)` pairs, where the index counts
+    /// paragraphs like [`paragraph_index_at`](Self::paragraph_index_at) and the
+    /// level is 0-based (0 for "heading 1"). The level comes from the paragraph's
+    /// own outline level if set, otherwise from its style in the style sheet.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ole::doc::Package;
+    ///
+    /// let mut pkg = Package::open("document.doc")?;
+    /// let doc = pkg.document()?;
+    /// for (index, level) in doc.outline_levels()? {
+    ///     println!("Paragraph {} is a level {} heading", index, level + 1);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn outline_levels(&self) -> Result<Vec<(usize, u8)>> {
+        let Some(pap_bin_table) = self.pap_bin_table.as_ref() else {
+            return Ok(Vec::new());
+        };
+        let stylesheet = StyleSheet::parse(&self.fib, &self.table_stream).unwrap_or_default();
+
+        // CP-aligned text, so character indices are CPs
+        let text = self.text_extractor.text();
+        let mut levels = Vec::new();
+        let paragraph_marks = text
+            .chars()
+            .enumerate()
+            .filter(|&(_, c)| is_paragraph_mark(c))
+            .map(|(cp, _)| cp as u32);
+        for (index, cp) in paragraph_marks.enumerate() {
+            let Some(pap) = pap_bin_table.properties_at(cp) else {
+                continue;
+            };
+            let level = match pap.outline_level {
+                // Level 9 marks body text
+                Some(level) => (level < 9).then_some(level),
+                None => pap
+                    .style_index
+                    .and_then(|istd| stylesheet.outline_level(istd)),
+            };
+            if let Some(level) = level {
+                levels.push((index, level));
+            }
+        }
+
+        Ok(levels)
+    }
+
+    /// Get the page setup of each section, in document order.
+    ///
+    /// Page size, orientation and margins come from the section's SEPX;
+    /// properties it does not set are `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ole::doc::Package;
+    ///
+    /// let mut pkg = Package::open("document.doc")?;
+    /// let doc = pkg.document()?;
+    /// for section in doc.sections()? {
+    ///     println!("{:?} x {:?} twips", section.page_width, section.page_height);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn sections(&self) -> Result<Vec<SectionProperties>> {
+        Ok(
+            SectionTable::parse(&self.fib, &self.table_stream, &self.word_document)
+                .map(|table| table.sections().to_vec())
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Parse the paragraph property bin table (PlcfBtePapx and its FKP pages).
+    fn parse_pap_bin_table(
+        fib: &FileInformationBlock,
+        table_stream: &[u8],
+        word_document: &[u8],
+    ) -> Option<PapBinTable> {
+        let piece_table = Self::parse_piece_table(fib, table_stream)?;
+
+        // Index 13 in FibRgFcLcb97 is fcPlcfBtePapx/lcbPlcfBtePapx (PLCFBTEPAPX)
+        let (offset, length) = fib.get_table_pointer(13)?;
+        let start = offset as usize;
+        let end = start.checked_add(length as usize)?.min(table_stream.len());
+        if start >= end {
+            return None;
+        }
+        PapBinTable::parse(&table_stream[start..end], word_document, &piece_table)
+    }
+
     // ──────────────────────────────────────────────────────────────────
     // Hyperlinks
     // ──────────────────────────────────────────────────────────────────
@@ -765,7 +984,7 @@ impl Document {
             return Ok(Vec::new());
         }
 
-        let text = Arc::new(self.text()?);
+        let text = Arc::new(self.text_extractor.text().to_owned());
 
         let para_extractor = ParagraphExtractor::new_with_range(
             &self.fib,
@@ -773,7 +992,8 @@ impl Document {
             Arc::clone(&text),
             self.chp_bin_table.as_ref(),
             (start_cp, end_cp),
-        )?;
+        )?
+        .with_pap_bin_table(self.pap_bin_table.as_ref());
 
         let extracted = para_extractor.extract_paragraphs()?;
         let mut paragraphs = Vec::with_capacity(extracted.len());
@@ -816,6 +1036,21 @@ impl Document {
         image.data(data_stream, word_document)
     }
 
+    /// Get the size, alt text and BLIP reference of an image.
+    ///
+    /// # Arguments
+    ///
+    /// * `image` - Reference to an Image obtained from `Run::image()`
+    pub fn image_info(
+        &self,
+        image: &super::image::Image,
+    ) -> std::result::Result<super::image::PictureInfo, super::image::ImageError> {
+        let data_stream = self.get_data_stream(image.pic_offset()).ok_or(
+            super::image::ImageError::InvalidPicOffset(image.pic_offset()),
+        )?;
+        image.info(data_stream)
+    }
+
     /// Get a reference to the WordDocument stream.
     ///
     /// This is useful for low-level image operations.
@@ -841,6 +1076,26 @@ impl Document {
         None
     }
 
+    /// Get the embedded OLE objects of the document.
+    ///
+    /// Returns the name of each storage below `ObjectPool` (such as
+    /// `_1207591883`) with the storage copied into a standalone compound file,
+    /// which [`ObjectStorage::parse`](crate::ole::object::ObjectStorage::parse)
+    /// reads.
+    #[inline]
+    pub fn object_pool(&self) -> &[(String, Vec<u8>)] {
+        &self.object_pool
+    }
+
+    /// Get the VBA project of the document, if it has macros.
+    ///
+    /// The `Macros` storage is returned as a standalone compound file, which
+    /// [`VbaProject::parse`](crate::ole::vba::VbaProject::parse) reads.
+    #[inline]
+    pub fn vba_project_data(&self) -> Option<&[u8]> {
+        self.vba_project_data.as_deref()
+    }
+
     /// Get a reference to the Data stream (if available).
     ///
     /// The Data stream contains embedded pictures and OLE objects.
@@ -868,13 +1123,18 @@ impl Document {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn paragraphs(&self) -> Result<Vec<Paragraph>> {
+        self.paragraphs_in(self.fib.get_all_subdoc_ranges())
+    }
+
+    /// Get the paragraphs of the subdocuments in `subdoc_ranges`, in order.
+    fn paragraphs_in(
+        &self,
+        subdoc_ranges: Vec<(&'static str, u32, u32)>,
+    ) -> Result<Vec<Paragraph>> {
         let mut all_paragraphs = Vec::new();
 
         // Wrap text in Arc to share across all extractors without cloning (thread-safe)
-        let text = Arc::new(self.text()?);
-
-        // Get all subdocument ranges from FIB
-        let subdoc_ranges = self.fib.get_all_subdoc_ranges();
+        let text = Arc::new(self.text_extractor.text().to_owned());
 
         // Pre-allocate if we know the approximate size
         if let Some((_, _, last_end)) = subdoc_ranges.last() {
@@ -897,7 +1157,8 @@ impl Document {
                 Arc::clone(&text),
                 self.chp_bin_table.as_ref(),
                 (start_cp, end_cp),
-            )?;
+            )?
+            .with_pap_bin_table(self.pap_bin_table.as_ref());
 
             let extracted_paras = para_extractor.extract_paragraphs()?;
 
@@ -905,9 +1166,137 @@ impl Document {
             self.convert_to_paragraphs(extracted_paras, &mut all_paragraphs);
         }
 
+        // The main document comes first, one paragraph per paragraph mark
+        self.apply_list_info(&text, &mut all_paragraphs);
+        self.apply_style_languages(&mut all_paragraphs);
+
         Ok(all_paragraphs)
     }
 
+    /// Resolve the languages the runs inherit from their paragraph and
+    /// character styles.
+    fn apply_style_languages(&self, paragraphs: &mut [Paragraph]) {
+        let Some(stylesheet) = StyleSheet::parse(&self.fib, &self.table_stream) else {
+            return;
+        };
+        for para in paragraphs {
+            // Paragraphs without a style use "Normal"
+            let paragraph_language =
+                stylesheet.language(para.properties().style_index.unwrap_or(0));
+            for run in para.runs_mut() {
+                let mut language = paragraph_language.clone();
+                if let Some(istd) = run.properties().style_index {
+                    language.apply(&stylesheet.language(istd));
+                }
+                run.set_style_language(language);
+            }
+        }
+    }
+
+    /// Get the document default languages as BCP-47 tags.
+    ///
+    /// Word documents store these as the languages of the "Normal" style,
+    /// which other styles are based on.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ole::doc::Package;
+    ///
+    /// let mut pkg = Package::open("document.doc")?;
+    /// let doc = pkg.document()?;
+    /// if let Some(tag) = doc.default_language().latin {
+    ///     println!("Written in {}", tag);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn default_language(&self) -> Language {
+        StyleSheet::parse(&self.fib, &self.table_stream)
+            .map(|stylesheet| stylesheet.language(0))
+            .unwrap_or_default()
+    }
+
+    /// Get the editing protection of the document.
+    ///
+    /// Combines the protection enforced in the document properties (DOP)
+    /// with the read-only recommendation and the password to modify, which
+    /// are FIB flags.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ole::doc::Package;
+    ///
+    /// let mut pkg = Package::open("document.doc")?;
+    /// let doc = pkg.document()?;
+    /// if doc.protection().is_protected() {
+    ///     println!("Editing is restricted: {:?}", doc.protection().kind);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn protection(&self) -> Protection {
+        let mut protection = DocumentProperties::parse(&self.fib, &self.table_stream)
+            .map(|dop| dop.protection())
+            .unwrap_or_default();
+        if self.fib.is_read_only_recommended() {
+            protection = protection.or(Protection::new(ProtectionKind::ReadOnlyRecommended));
+        }
+        if self.fib.is_write_reserved() {
+            protection = protection.or(Protection::with_password(ProtectionKind::ReadOnly));
+        }
+        protection
+    }
+
+    /// Compute the list numbering of the main-document paragraphs.
+    ///
+    /// The list (ilfo) and level (ilvl) come from the paragraph properties at
+    /// each paragraph mark, or else from the paragraph style. Labels count the
+    /// items of each list in document order.
+    fn apply_list_info(&self, text: &str, paragraphs: &mut [Paragraph]) {
+        let Some(tables) = self.list_tables.as_ref() else {
+            return;
+        };
+        if tables.overrides().is_empty() {
+            return;
+        }
+        let Some(pap_bin_table) = self.pap_bin_table.as_ref() else {
+            return;
+        };
+        let stylesheet = StyleSheet::parse(&self.fib, &self.table_stream).unwrap_or_default();
+        let mut counter = ListCounter::new();
+
+        let (_, main_end) = self.fib.get_main_doc_range();
+        let paragraph_marks = text
+            .chars()
+            .take(main_end as usize)
+            .enumerate()
+            .filter(|&(_, c)| is_paragraph_mark(c))
+            .map(|(cp, _)| cp as u32);
+        for (para, cp) in paragraphs.iter_mut().zip(paragraph_marks) {
+            let Some(pap) = pap_bin_table.properties_at(cp) else {
+                continue;
+            };
+            let list = match pap.list_format_override {
+                Some(ilfo) => Some((ilfo, pap.list_level)),
+                None => pap
+                    .style_index
+                    .and_then(|istd| stylesheet.list_format(istd))
+                    .map(|(ilfo, ilvl)| (ilfo, pap.list_level.or(Some(ilvl)))),
+            };
+            // ilfo is a 1-based index into the LFOs; 0 means "not in a list"
+            let Some((ilfo, ilvl)) = list.filter(|&(ilfo, _)| ilfo > 0) else {
+                continue;
+            };
+            let Some(lfo) = tables.overrides().get(ilfo as usize - 1) else {
+                continue;
+            };
+            let Some(list) = tables.find_structure(lfo.list_id) else {
+                continue;
+            };
+            para.set_list_info(counter.next(lfo.list_id, ilvl.unwrap_or(0), &list.level_formats()));
+        }
+    }
+
     // fn has_picture(&self, picture_offset: u32) -> bool {}
 
     /// Convert extracted paragraph data to Paragraph objects.
@@ -1006,7 +1395,7 @@ impl Document {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn tables(&self) -> Result<Vec<Table>> {
-        self.extract_tables_from_paragraphs(&self.paragraphs()?, 1)
+        Self::extract_tables_from_paragraphs(&self.paragraphs()?, 1)
     }
 
     /// Get all document elements (paragraphs and tables) in document order.
@@ -1044,10 +1433,37 @@ impl Document {
     /// by scanning paragraph properties, which is significantly faster than calling
     /// `paragraphs()` and `tables()` separately.
     pub fn elements(&self) -> Result<Vec<crate::document::DocumentElement>> {
+        // Extract all paragraphs once
+        Self::group_elements(&self.paragraphs()?)
+    }
+
+    /// Get the paragraphs and tables of the main document in document order.
+    ///
+    /// Like [`elements`](Self::elements), but without the headers, footers,
+    /// notes, comments and text boxes, which follow the main document in the
+    /// text stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ole::doc::Package;
+    ///
+    /// let mut pkg = Package::open("document.doc")?;
+    /// let doc = pkg.document()?;
+    /// println!("Body elements: {}", doc.body_elements()?.len());
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn body_elements(&self) -> Result<Vec<crate::document::DocumentElement>> {
+        let (start, end) = self.fib.get_main_doc_range();
+        Self::group_elements(&self.paragraphs_in(vec![("Main Document", start, end)])?)
+    }
+
+    /// Group `paragraphs` into paragraphs and top-level tables.
+    pub(crate) fn group_elements(
+        paragraphs: &[Paragraph],
+    ) -> Result<Vec<crate::document::DocumentElement>> {
         use crate::document::DocumentElement;
 
-        // Extract all paragraphs once
-        let paragraphs = self.paragraphs()?;
         let mut elements = Vec::new();
         let mut i = 0;
 
@@ -1075,11 +1491,13 @@ impl Document {
                 }
 
                 // Extract rows from the collected table paragraphs
-                let rows = self.extract_rows_from_table_paragraphs(&table_paras, 1)?;
+                let rows = Self::extract_rows_from_table_paragraphs(&table_paras, 1)?;
 
                 if !rows.is_empty() {
                     elements.push(DocumentElement::Table(Box::new(
-                        crate::document::Table::Doc(Table::new(rows)),
+                        crate::document::Table::Doc(
+                            Table::from_rows(rows).with_paragraph_count(table_paras.len()),
+                        ),
                     )));
                 }
             } else if !props.in_table {
@@ -1111,11 +1529,7 @@ impl Document {
     /// # Returns
     ///
     /// Vector of Table objects found at the specified nesting level
-    fn extract_tables_from_paragraphs(
-        &self,
-        paragraphs: &[Paragraph],
-        level: i32,
-    ) -> Result<Vec<Table>> {
+    fn extract_tables_from_paragraphs(paragraphs: &[Paragraph], level: i32) -> Result<Vec<Table>> {
         let mut tables = Vec::new();
         let mut i = 0;
 
@@ -1143,10 +1557,10 @@ impl Document {
                 }
 
                 // Now extract rows from the collected table paragraphs
-                let rows = self.extract_rows_from_table_paragraphs(&table_paras, level)?;
+                let rows = Self::extract_rows_from_table_paragraphs(&table_paras, level)?;
 
                 if !rows.is_empty() {
-                    tables.push(Table::new(rows));
+                    tables.push(Table::from_rows(rows));
                 }
             } else {
                 i += 1;
@@ -1158,7 +1572,8 @@ impl Document {
 
     /// Extract rows from table paragraphs.
     ///
-    /// Groups consecutive paragraphs into rows based on the is_table_row_end marker.
+    /// Groups consecutive paragraphs into rows based on the row end markers,
+    /// whose paragraph properties carry the table definition (TAP) of the row.
     /// Based on Apache POI's Table.initRows() logic.
     ///
     /// # Arguments
@@ -1170,7 +1585,6 @@ impl Document {
     ///
     /// Vector of Row objects
     fn extract_rows_from_table_paragraphs(
-        &self,
         table_paras: &[Paragraph],
         level: i32,
     ) -> Result<Vec<super::table::Row>> {
@@ -1182,21 +1596,28 @@ impl Document {
         for para in table_paras {
             let props = para.properties();
 
-            // Skip paragraphs from nested tables (higher level)
-            if props.table_nesting_level > level {
-                continue;
-            }
-
-            // Add paragraph to current row
+            // Add paragraph to current row; paragraphs of nested tables stay
+            // with the cell they are nested in
             current_row_paras.push(para.clone());
 
-            // Check if this paragraph marks the end of a row
-            if props.is_table_row_end && props.table_nesting_level == level {
+            // Check if this paragraph marks the end of a row; rows of nested
+            // tables end with an inner row end marker
+            let row_end = if level > 1 {
+                props.inner_table_row_end
+            } else {
+                props.is_table_row_end
+            };
+            if row_end && props.table_nesting_level == level {
                 // End of row - create cells from the collected paragraphs
-                let cells = self.extract_cells_from_row_paragraphs(&current_row_paras)?;
+                let tap = props.table_properties.as_deref();
+                let cells =
+                    Self::extract_cells_from_row_paragraphs(&current_row_paras, level, tap)?;
 
                 if !cells.is_empty() {
-                    rows.push(Row::new(cells));
+                    rows.push(match tap {
+                        Some(tap) => Row::with_properties(cells, tap.clone()),
+                        None => Row::new(cells),
+                    });
                 }
 
                 current_row_paras.clear();
@@ -1205,7 +1626,7 @@ impl Document {
 
         // Handle any remaining paragraphs (incomplete row)
         if !current_row_paras.is_empty() {
-            let cells = self.extract_cells_from_row_paragraphs(&current_row_paras)?;
+            let cells = Self::extract_cells_from_row_paragraphs(&current_row_paras, level, None)?;
             if !cells.is_empty() {
                 rows.push(Row::new(cells));
             }
@@ -1216,44 +1637,61 @@ impl Document {
 
     /// Extract cells from row paragraphs.
     ///
-    /// Each cell typically consists of one or more paragraphs.
-    /// The exact cell boundaries are determined by table properties (TAP).
-    /// For now, we create a simple cell structure from the paragraphs.
+    /// Each cell consists of one or more paragraphs, the last of which ends
+    /// with a cell mark. Paragraphs at a deeper nesting level belong to tables
+    /// nested in the cell. The cells take their properties from the row's
+    /// TAP, in order.
     ///
     /// # Arguments
     ///
     /// * `row_paras` - Paragraphs belonging to a row
+    /// * `level` - Table nesting level
+    /// * `tap` - Table definition of the row, if known
     ///
     /// # Returns
     ///
     /// Vector of Cell objects
     fn extract_cells_from_row_paragraphs(
-        &self,
         row_paras: &[Paragraph],
+        level: i32,
+        tap: Option<&TableProperties>,
     ) -> Result<Vec<super::table::Cell>> {
         use super::table::Cell;
 
-        // For a proper implementation, we'd need to parse TAP (Table Properties)
-        // to get exact cell boundaries. For now, we create one cell per paragraph
-        // which is a simplified approach but works for basic tables.
-
         let mut cells = Vec::new();
+        let mut cell_paras = Vec::new();
 
-        // Group paragraphs into cells
-        // In Word's binary format, cell boundaries are marked in table properties
-        // For now, we use a simple heuristic: each paragraph is a cell
-        // unless it's the row-end marker
         for para in row_paras {
             let props = para.properties();
+            let own = props.table_nesting_level <= level;
 
             // Skip the row-end marker paragraph as it doesn't contain cell content
-            if props.is_table_row_end {
+            if own && (props.is_table_row_end || props.inner_table_row_end) {
                 continue;
             }
 
-            // Create a cell with this paragraph
-            let cell = Cell::with_properties(vec![para.clone()], None);
-            cells.push(cell);
+            cell_paras.push(para.clone());
+
+            // Cells of nested tables end with an inner cell marker
+            let cell_end = if level > 1 {
+                props.inner_table_cell
+            } else {
+                props.ends_cell
+            };
+            if own && cell_end {
+                let properties = tap.and_then(|tap| tap.cell_properties.get(cells.len()).cloned());
+                cells.push(Self::build_cell(
+                    std::mem::take(&mut cell_paras),
+                    level,
+                    properties,
+                )?);
+            }
+        }
+
+        // Paragraphs after the last cell mark form one more cell
+        if !cell_paras.is_empty() {
+            let properties = tap.and_then(|tap| tap.cell_properties.get(cells.len()).cloned());
+            cells.push(Self::build_cell(cell_paras, level, properties)?);
         }
 
         // If we have no cells but have a row-end marker, create at least one empty cell
@@ -1268,15 +1706,82 @@ impl Document {
 
         Ok(cells)
     }
+
+    /// Build a cell from its paragraphs, collecting the runs of deeper
+    /// paragraphs into nested tables.
+    fn build_cell(
+        paragraphs: Vec<Paragraph>,
+        level: i32,
+        properties: Option<CellProperties>,
+    ) -> Result<super::table::Cell> {
+        let mut own = Vec::new();
+        let mut tables = Vec::new();
+        let mut nested = Vec::new();
+
+        for para in paragraphs {
+            if para.properties().table_nesting_level > level {
+                nested.push(para);
+                continue;
+            }
+            if !nested.is_empty() {
+                let found =
+                    Self::extract_tables_from_paragraphs(&std::mem::take(&mut nested), level + 1)?;
+                tables.extend(found.into_iter().map(|table| (own.len(), table)));
+            }
+            own.push(para);
+        }
+        if !nested.is_empty() {
+            let found = Self::extract_tables_from_paragraphs(&nested, level + 1)?;
+            tables.extend(found.into_iter().map(|table| (own.len(), table)));
+        }
+
+        let cell = super::table::Cell::with_properties(own, properties);
+        Ok(if tables.is_empty() {
+            cell
+        } else {
+            cell.with_tables(tables)
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use super::super::Package;
     #[cfg(feature = "imgconv")]
-    use super::super::{Image, ImageError, Package};
-    #[cfg(feature = "imgconv")]
+    use super::super::{Image, ImageError};
     use std::path::Path;
 
+    fn open_fixture(name: &str) -> Package {
+        let base = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let doc_path = base.join("test-data").join("ole").join("doc").join(name);
+        Package::open(&doc_path).expect("open doc")
+    }
+
+    #[test]
+    fn test_text_in_language_codepage() {
+        // 8-bit pieces of a Russian document are in Windows-1251
+        let mut pkg = open_fixture("Cp1251.doc");
+        let text = pkg.document().expect("load document").text().unwrap();
+        assert_eq!(
+            text.trim_end(),
+            "Привет, мир!\rСъешь же ещё этих мягких французских булок."
+        );
+    }
+
+    #[test]
+    fn test_symbol_font_bullets() {
+        // Bullets drawn with the Symbol font map to U+2022
+        let mut pkg = open_fixture("SymbolBullets.doc");
+        let doc = pkg.document().expect("load document");
+        let text = doc.text().unwrap();
+        assert_eq!(text.trim_end(), "•\tFirst item\r•\tSecond item");
+
+        let paragraphs = doc.paragraphs().expect("paragraphs");
+        let runs = paragraphs[1].runs().expect("runs");
+        let run_text: String = runs.iter().map(|run| run.text().unwrap()).collect();
+        assert_eq!(run_text, "•\tSecond item");
+    }
+
     #[cfg(feature = "imgconv")]
     #[test]
     fn test_extract_png_image_from_doc() {
@@ -1325,4 +1830,66 @@ mod tests {
         let err = doc.image_data(&img).expect_err("expected invalid offset");
         assert!(matches!(err, ImageError::InvalidPicOffset(_)));
     }
+
+    #[test]
+    fn test_nested_table_paragraphs() {
+        use super::super::paragraph::Paragraph;
+        use super::super::parts::pap::ParagraphProperties;
+
+        let para = |text: &str, level: i32, setup: fn(&mut ParagraphProperties)| {
+            let mut props = ParagraphProperties {
+                in_table: true,
+                table_nesting_level: level,
+                ..Default::default()
+            };
+            setup(&mut props);
+            Paragraph::with_properties(text.to_string(), props)
+        };
+        // One outer row whose second cell holds a 1x2 table followed by a paragraph
+        let paragraphs = vec![
+            para("A", 1, |p| p.ends_cell = true),
+            para("X", 2, |p| p.inner_table_cell = true),
+            para("Y", 2, |p| p.inner_table_cell = true),
+            para("", 2, |p| {
+                p.inner_table_cell = true;
+                p.inner_table_row_end = true;
+            }),
+            para("tail", 1, |p| p.ends_cell = true),
+            para("", 1, |p| {
+                p.ends_cell = true;
+                p.is_table_row_end = true;
+            }),
+        ];
+
+        let tables = super::Document::extract_tables_from_paragraphs(&paragraphs, 1).unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].row_count().unwrap(), 1);
+
+        let cells = tables[0].rows().unwrap()[0].cells().unwrap();
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].text().unwrap(), "A");
+        assert!(cells[0].tables().unwrap().is_empty());
+
+        let nested = cells[1].tables().unwrap();
+        assert_eq!(nested.len(), 1);
+        let nested_cells = nested[0].rows().unwrap()[0].cells().unwrap();
+        let nested_texts: Vec<&str> = nested_cells.iter().map(|c| c.text().unwrap()).collect();
+        assert_eq!(nested_texts, vec!["X", "Y"]);
+
+        let own: Vec<String> = cells[1]
+            .paragraphs()
+            .unwrap()
+            .iter()
+            .map(|p| p.text().unwrap().to_string())
+            .collect();
+        assert_eq!(own, vec!["tail"]);
+        let kinds: Vec<bool> = cells[1]
+            .elements()
+            .unwrap()
+            .iter()
+            .map(|e| e.is_table())
+            .collect();
+        assert_eq!(kinds, vec![true, false]);
+        assert_eq!(cells[1].text().unwrap(), "X\nY\ntail");
+    }
 }
@@ -8,6 +8,8 @@ use super::parts::headers::HeaderFooterType;
 pub struct HeaderFooter {
     /// Type of header/footer
     pub header_footer_type: HeaderFooterType,
+    /// Index of the section the header/footer belongs to (0-based)
+    pub section_index: usize,
     /// Text content
     pub text: String,
     /// Paragraphs in this header/footer
@@ -19,6 +21,7 @@ impl HeaderFooter {
     pub fn new(header_footer_type: HeaderFooterType, text: String) -> Self {
         Self {
             header_footer_type,
+            section_index: 0,
             text,
             paragraphs: Vec::new(),
         }
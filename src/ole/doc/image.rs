@@ -184,6 +184,50 @@ const _: () = {
     assert!(std::mem::size_of::<PictureFields>() == 0x44);
 };
 
+const EMU_PER_TWIP: u64 = 635;
+
+/// Size and alt text of an embedded picture.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PictureInfo {
+    /// Displayed width in EMU
+    pub width_emu: u64,
+    /// Displayed height in EMU
+    pub height_emu: u64,
+    /// Alt text (`wzDescription` shape property)
+    pub description: Option<String>,
+    /// Shape name (`wzName`), or the picture name of the PICF header
+    pub name: Option<String>,
+    /// 1-based index of the picture's BSE in the BLIP store (`pib`)
+    pub blip_index: Option<u32>,
+    /// File extension of the stored picture, such as `png` or `emf`
+    pub extension: Option<&'static str>,
+}
+
+/// File extension for a BLIP record type (`0xF018 + btWin32` for BSEs).
+fn blip_extension(record_type: u16) -> Option<&'static str> {
+    Some(match record_type {
+        0xF01A => "emf",
+        0xF01B => "wmf",
+        0xF01C => "pict",
+        0xF01D | 0xF02A => "jpg",
+        0xF01E => "png",
+        0xF01F => "dib",
+        0xF029 => "tiff",
+        _ => return None,
+    })
+}
+
+/// Decode a null-terminated UTF-16LE property value.
+fn utf16z(data: &[u8]) -> Option<String> {
+    let units: Vec<u16> = data
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .take_while(|&u| u != 0)
+        .collect();
+    let text = String::from_utf16_lossy(&units);
+    (!text.is_empty()).then_some(text)
+}
+
 // ============================================================================
 // Image struct - metadata only, data loaded lazily
 // ============================================================================
@@ -211,6 +255,109 @@ impl Image {
         self.pic_offset
     }
 
+    /// Parse the picture header, returning the PICF fields, the picture
+    /// name (only present when `mm == 0x66`) and the offset of the first
+    /// Escher record.
+    fn header<'a>(
+        &self,
+        data_stream: &'a [u8],
+    ) -> Result<(PictureFields, &'a [u8], usize), ImageError> {
+        let mut offset = self.pic_offset as usize;
+
+        let pic_fields = PictureFields::try_parse(data_stream, offset)
+            .ok_or(ImageError::InvalidPicOffset(self.pic_offset))?;
+
+        offset += std::mem::size_of::<PictureFields>();
+
+        let mut name: &[u8] = &[];
+        if pic_fields.mm == 0x66 {
+            let cch_pic_name = *data_stream
+                .get(offset)
+                .ok_or(ImageError::InvalidPicOffset(self.pic_offset))?;
+            offset += 1;
+            name = data_stream
+                .get(offset..offset + cch_pic_name as usize)
+                .unwrap_or_default();
+            offset += cch_pic_name as usize;
+        }
+
+        Ok((pic_fields, name, offset))
+    }
+
+    /// Get the size, alt text and BLIP reference of the picture.
+    ///
+    /// Unlike [`Image::data`] this does not decode the picture itself.
+    pub fn info(&self, data_stream: &[u8]) -> Result<PictureInfo, ImageError> {
+        use crate::ole::escher::{
+            EscherContainer, EscherProperties, EscherPropertyId, EscherRecord, EscherRecordType,
+        };
+
+        let (pic_fields, name, offset) = self.header(data_stream)?;
+
+        // The displayed size is the goal size scaled by mx/my (in 1/1000)
+        let scaled = |goal: i16, scale: i16| {
+            let scale = if scale > 0 { scale as u64 } else { 1000 };
+            goal.max(0) as u64 * scale / 1000 * EMU_PER_TWIP
+        };
+        let mut info = PictureInfo {
+            width_emu: scaled(pic_fields.dxa_goal, pic_fields.mx),
+            height_emu: scaled(pic_fields.dya_goal, pic_fields.my),
+            name: (!name.is_empty()).then(|| String::from_utf8_lossy(name).into_owned()),
+            ..Default::default()
+        };
+
+        let Ok((record, record_size)) = EscherRecord::parse(data_stream, offset) else {
+            return Ok(info);
+        };
+
+        // The BSE or BLIP follows the SpContainer. lcb comes from the file,
+        // so keep the end within the data stream.
+        let lcb = usize::try_from(pic_fields.lcb).unwrap_or(0);
+        let end = (self.pic_offset as usize)
+            .checked_add(lcb)
+            .map_or(data_stream.len(), |end| end.min(data_stream.len()));
+        let mut next = offset + record_size;
+        while next < end {
+            let Ok((blip, size)) = EscherRecord::parse(data_stream, next) else {
+                break;
+            };
+            info.extension = match blip.record_type_raw {
+                0xF007 => blip
+                    .data
+                    .first()
+                    .and_then(|&bt| blip_extension(bt as u16 + 0xF018)),
+                raw => blip_extension(raw),
+            };
+            if info.extension.is_some() {
+                break;
+            }
+            next += size;
+        }
+
+        // Shape properties live in the OPT records of the picture's SpContainer
+        if record.record_type != EscherRecordType::SpContainer {
+            return Ok(info);
+        }
+        let container = EscherContainer::new(record);
+        for opt_type in [EscherRecordType::Opt, EscherRecordType::TertiaryOpt] {
+            let Some(opt) = container.find_child(opt_type) else {
+                continue;
+            };
+            let props = EscherProperties::from_opt_record(&opt);
+            if let Some(text) = props.get_binary(EscherPropertyId::GroupDescription) {
+                info.description = utf16z(text);
+            }
+            if let Some(text) = props.get_binary(EscherPropertyId::GroupName) {
+                info.name = utf16z(text).or(info.name);
+            }
+            if let Some(pib) = props.get_int(EscherPropertyId::BlipToDisplay) {
+                info.blip_index = u32::try_from(pib).ok().filter(|&i| i > 0);
+            }
+        }
+
+        Ok(info)
+    }
+
     /// Get raw image data from the document buffer (zero-copy when possible).
     ///
     /// This method extracts and optionally decompresses the image data.
@@ -227,22 +374,7 @@ impl Image {
     ) -> Result<crate::images::ExtractedImage<'static>, ImageError> {
         use crate::{images::ImageExtractor, ole::escher::EscherRecord};
 
-        let mut offset = self.pic_offset as usize;
-
-        let pic_fields = PictureFields::try_parse(data_stream, offset)
-            .ok_or(ImageError::InvalidPicOffset(self.pic_offset))?;
-
-        offset += std::mem::size_of::<PictureFields>();
-
-        // Handle picture name if mm == 0x66
-        if pic_fields.mm == 0x66 {
-            let cch_pic_name = match data_stream.get(offset) {
-                Some(&b) => b,
-                None => return Err(ImageError::InvalidPicOffset(self.pic_offset)),
-            };
-            offset += 1;
-            offset += cch_pic_name as usize;
-        }
+        let (pic_fields, _, mut offset) = self.header(data_stream)?;
 
         // Parse the first Escher record (usually SpContainer or BStoreContainer)
         let (_, record_size) = EscherRecord::parse(data_stream, offset)
@@ -411,4 +543,17 @@ mod tests {
             Err(ImageError::InvalidBlockType(0xFF))
         ));
     }
+
+    #[test]
+    fn test_info_with_negative_lcb() {
+        // PICF at offset 4 claiming a negative length, followed by an empty
+        // SpContainer
+        let mut data = vec![0u8; 4 + 0x44];
+        data[4..8].copy_from_slice(&(-1i32).to_le_bytes());
+        data.extend_from_slice(&[0x0F, 0x00, 0x04, 0xF0, 0, 0, 0, 0]);
+
+        let info = Image::new(4).info(&data).unwrap();
+        assert_eq!(info.extension, None);
+        assert_eq!(info.description, None);
+    }
 }
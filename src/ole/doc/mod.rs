@@ -56,8 +56,10 @@ pub mod image;
 pub mod package;
 pub mod paragraph;
 pub mod parts;
+pub(crate) mod reader;
 pub mod shapes;
 pub mod table;
+pub mod text_box;
 
 /// DOC file writing
 pub mod writer;
@@ -66,10 +68,12 @@ pub use document::Document;
 pub use footnote::{Endnote, Footnote};
 pub use header_footer::HeaderFooter;
 pub use hyperlink::Hyperlink;
-pub use image::{Image, ImageError};
+pub use image::{Image, ImageError, PictureInfo};
 pub use package::Package;
 pub use paragraph::{Paragraph, Run};
 pub use parts::numbering::{ListLevel, ListTables, NumberFormat};
+pub use parts::sections::SectionProperties;
 pub use shapes::DocShape;
 pub use table::{Cell, Row, Table};
+pub use text_box::TextBox;
 pub use writer::{CharacterFormatting, DocWriteError, DocWriter, ParagraphFormatting};
@@ -1,6 +1,7 @@
 use super::super::{OleError, OleFile};
 /// Package implementation for legacy Word documents (.doc).
 use super::document::Document;
+use super::parts::fib::FileInformationBlock;
 use std::fs::File;
 use std::io::{self, Read, Seek};
 use std::path::Path;
@@ -175,6 +176,24 @@ impl<R: Read + Seek> Package<R> {
         Document::from_ole(&mut self.ole)
     }
 
+    /// Check that the document can be parsed, without parsing it.
+    ///
+    /// Reads the File Information Block and locates the table stream it
+    /// names, so that a damaged file is rejected when it is opened rather
+    /// than on first use.
+    pub(crate) fn validate(&mut self) -> Result<()> {
+        let fib = FileInformationBlock::read(&mut self.ole)?;
+        let table_stream_name = if fib.which_table_stream() {
+            "1Table"
+        } else {
+            "0Table"
+        };
+        self.ole
+            .stream_location(&[table_stream_name])
+            .map_err(|_| DocError::StreamNotFound(table_stream_name.to_string()))?;
+        Ok(())
+    }
+
     /// Get the underlying OLE file.
     ///
     /// This provides access to lower-level OLE operations and streams.
@@ -1,6 +1,8 @@
 /// Paragraph and Run structures for legacy Word documents.
 use super::package::Result;
 use super::parts::chp::{CharacterProperties, UnderlineStyle, VerticalPosition};
+use crate::common::Language;
+use crate::document::ListInfo;
 use std::sync::Arc;
 
 /// A paragraph in a Word document.
@@ -22,6 +24,8 @@ pub struct Paragraph {
     runs: Vec<Run>,
     /// Paragraph formatting properties (PAP)
     properties: super::parts::pap::ParagraphProperties,
+    /// List numbering, computed by the document for main-document paragraphs
+    list_info: Option<ListInfo>,
 }
 
 impl Paragraph {
@@ -47,6 +51,7 @@ impl Paragraph {
             text,
             runs,
             properties: super::parts::pap::ParagraphProperties::default(),
+            list_info: None,
         }
     }
 
@@ -62,6 +67,7 @@ impl Paragraph {
             text,
             runs,
             properties: super::parts::pap::ParagraphProperties::default(),
+            list_info: None,
         }
     }
 
@@ -75,6 +81,7 @@ impl Paragraph {
             text,
             runs: Vec::new(),
             properties,
+            list_info: None,
         }
     }
 
@@ -102,6 +109,11 @@ impl Paragraph {
         self.runs = runs;
     }
 
+    /// Get mutable access to the runs of this paragraph (internal use).
+    pub(crate) fn runs_mut(&mut self) -> &mut [Run] {
+        &mut self.runs
+    }
+
     /// Set the paragraph properties (internal use).
     pub(crate) fn set_properties(&mut self, properties: super::parts::pap::ParagraphProperties) {
         self.properties = properties;
@@ -112,6 +124,21 @@ impl Paragraph {
         &self.properties
     }
 
+    /// Get the list numbering of this paragraph.
+    ///
+    /// Only set on main-document paragraphs returned by
+    /// [`Document::paragraphs`](super::Document::paragraphs), since the label
+    /// depends on the list items before the paragraph.
+    #[inline]
+    pub fn list_info(&self) -> Option<&ListInfo> {
+        self.list_info.as_ref()
+    }
+
+    /// Set the list numbering computed by the document (internal use).
+    pub(crate) fn set_list_info(&mut self, list_info: Option<ListInfo>) {
+        self.list_info = list_info;
+    }
+
     /// Extract all MTEF formulas from this paragraph as LaTeX.
     ///
     /// Returns a vector of LaTeX formula strings found in any run within this paragraph.
@@ -180,6 +207,8 @@ pub struct Run {
     mtef_formula_ast: Option<Arc<Vec<()>>>,
     /// Embedded image (metadata only, data loaded lazily via Document::image_data)
     image: Option<super::image::Image>,
+    /// Languages inherited from the paragraph and character styles
+    style_language: Language,
 }
 
 impl Run {
@@ -190,6 +219,7 @@ impl Run {
             properties,
             mtef_formula_ast: None,
             image: None,
+            style_language: Language::default(),
         }
     }
 
@@ -205,6 +235,7 @@ impl Run {
             properties,
             mtef_formula_ast: Some(mtef_ast),
             image: None,
+            style_language: Language::default(),
         }
     }
 
@@ -220,6 +251,7 @@ impl Run {
             properties,
             mtef_formula_ast: None,
             image: None,
+            style_language: Language::default(),
         }
     }
 
@@ -234,6 +266,7 @@ impl Run {
             properties,
             mtef_formula_ast: None,
             image: Some(image),
+            style_language: Language::default(),
         }
     }
 
@@ -283,6 +316,13 @@ impl Run {
         self.properties.is_strikethrough
     }
 
+    /// Check if this run is right-to-left (sprmCFBiDi).
+    ///
+    /// Returns `None` if not specified.
+    pub fn rtl(&self) -> Option<bool> {
+        self.properties.is_bidi
+    }
+
     /// Get the font size for this run in half-points.
     ///
     /// Returns the size if specified, None if inherited.
@@ -316,6 +356,16 @@ impl Run {
         self.properties.is_all_caps
     }
 
+    /// Check if this run is tracked as deleted.
+    pub fn is_deleted(&self) -> bool {
+        self.properties.is_deleted
+    }
+
+    /// Check if this run is tracked as inserted.
+    pub fn is_inserted(&self) -> bool {
+        self.properties.is_inserted
+    }
+
     /// Get the character properties for this run.
     ///
     /// Provides access to all formatting properties.
@@ -323,6 +373,25 @@ impl Run {
         &self.properties
     }
 
+    /// Get the declared languages of this run as BCP-47 tags.
+    ///
+    /// For runs of paragraphs returned by
+    /// [`Document::paragraphs`](super::Document::paragraphs), languages not
+    /// set on the run come from its character style, then from the
+    /// paragraph style and the styles it is based on, normally ending at
+    /// "Normal". For other runs, only the languages set on the run itself
+    /// are returned.
+    pub fn language(&self) -> Language {
+        let mut language = self.style_language.clone();
+        language.apply(&self.properties.language());
+        language
+    }
+
+    /// Set the languages inherited from styles (internal use).
+    pub(crate) fn set_style_language(&mut self, language: Language) {
+        self.style_language = language;
+    }
+
     /// Check if this run contains an MTEF formula.
     ///
     /// Returns true if this run contains a parsed MTEF formula AST.
@@ -9,6 +9,7 @@
 ///
 /// Based on Apache POI's CharacterSprmUncompressor and CharacterProperties.
 use super::super::package::Result;
+use crate::common::Language;
 use crate::ole::sprm::{Sprm, parse_sprms};
 use crate::ole::sprm_operations::*;
 
@@ -54,6 +55,9 @@ pub struct CharacterProperties {
     pub is_obj: bool,
     /// Special character flag (fSpec)
     pub is_spec: bool,
+    /// Symbol character inserted from a symbol font (sprmCSymbol): the font
+    /// index (ftc) and character code
+    pub symbol: Option<(u16, u16)>,
     /// Data flag (fData) - if true, pic_offset points to NilPICFAndBinData, not picture
     pub is_data: bool,
     /// Picture offset for embedded objects (fc in Data stream)
@@ -74,12 +78,22 @@ pub struct CharacterProperties {
     pub kerning: Option<u16>,
     /// Character scale percentage
     pub char_scale: Option<u16>,
-    /// Language ID
+    /// Language ID of Latin text (sprmCRgLid0)
     pub language_id: Option<u16>,
+    /// Language ID of East Asian text (sprmCRgLid1)
+    pub language_id_fe: Option<u16>,
+    /// Language ID of complex script text (sprmCLidBi)
+    pub language_id_bidi: Option<u16>,
+    /// Right-to-left text (sprmCFBiDi)
+    pub is_bidi: Option<bool>,
     /// Style index (istd)
     pub style_index: Option<u16>,
     /// Vanish (hidden)
     pub is_vanish: Option<bool>,
+    /// Tracked as deleted (fRMarkDel)
+    pub is_deleted: bool,
+    /// Tracked as inserted (fRMark)
+    pub is_inserted: bool,
 }
 
 /// Underline styles supported in DOC format.
@@ -175,11 +189,15 @@ impl CharacterProperties {
         match operation {
             // Operation 0x00: sprmCFRMarkDel - Mark deleted revision
             0x00 => {
-                // Not commonly used in basic text extraction
+                if let Some(val) = sprm.operand_byte() {
+                    chp.is_deleted = val != 0;
+                }
             },
             // Operation 0x01: sprmCFRMark - Mark revision
             0x01 => {
-                // Not commonly used in basic text extraction
+                if let Some(val) = sprm.operand_byte() {
+                    chp.is_inserted = val != 0;
+                }
             },
             // Operation 0x02: sprmCFFldVanish - Field vanish flag
             0x02 => {
@@ -203,7 +221,6 @@ impl CharacterProperties {
             // Operation 0x06: sprmCFData - Data flag
             0x06 => {
                 // Data field flag
-                debug_assert_eq!(sprm.size, 2);
                 if let Some(val) = sprm.operand_byte() {
                     chp.is_data = val != 0;
                 }
@@ -219,7 +236,9 @@ impl CharacterProperties {
             // Operation 0x09: sprmCSymbol - Symbol character
             0x09 => {
                 chp.is_spec = true;
-                // Symbol character - would need font and character code
+                if let Some(operand) = sprm.operand_dword() {
+                    chp.symbol = Some((operand as u16, (operand >> 16) as u16));
+                }
             },
             // Operation 0x0A: sprmCFOle2 - OLE2 object flag
             0x0A => {
@@ -591,9 +610,33 @@ impl CharacterProperties {
                     chp.color = Some((r, g, b));
                 }
             },
-            // Operations 0x5A-0x6F, 0x71-0x75: Various bi-directional, borders, shading, etc.
-            0x5A..=0x6F | 0x71..=0x75 => {
-                // Bi-directional, borders, shading, language IDs, etc.
+            // Operations 0x6D, 0x73: sprmCRgLid0_80, sprmCRgLid0 - Latin language ID
+            0x6D | 0x73 => {
+                if let Some(lid) = sprm.operand_word() {
+                    chp.language_id = Some(lid);
+                }
+            },
+            // Operations 0x6E, 0x74: sprmCRgLid1_80, sprmCRgLid1 - East Asian language ID
+            0x6E | 0x74 => {
+                if let Some(lid) = sprm.operand_word() {
+                    chp.language_id_fe = Some(lid);
+                }
+            },
+            // Operation 0x5F: sprmCLidBi - Complex script language ID
+            0x5F => {
+                if let Some(lid) = sprm.operand_word() {
+                    chp.language_id_bidi = Some(lid);
+                }
+            },
+            // Operation 0x5A: sprmCFBiDi - Right-to-left text
+            0x5A => {
+                if let Some(val) = sprm.operand_byte() {
+                    chp.is_bidi = Some(val != 0);
+                }
+            },
+            // Operations 0x5B-0x6F, 0x71-0x75: Various bi-directional, borders, shading, etc.
+            0x5B..=0x6F | 0x71..=0x75 => {
+                // Bi-directional, borders, shading, etc.
                 // Not commonly needed for basic text extraction
             },
             // Default: Unknown or unsupported SPRM
@@ -629,6 +672,11 @@ impl CharacterProperties {
         }
     }
 
+    /// Get the languages set by these properties.
+    pub fn language(&self) -> Language {
+        Language::from_lcids(self.language_id, self.language_id_fe, self.language_id_bidi)
+    }
+
     /// Check if any formatting is applied.
     pub fn has_formatting(&self) -> bool {
         self.is_bold.is_some()
@@ -655,6 +703,41 @@ mod tests {
         assert!(!chp.has_formatting());
     }
 
+    #[test]
+    fn test_revision_marks() {
+        // sprmCFRMarkDel followed by sprmCFRMarkIns (cleared)
+        let chp = CharacterProperties::from_sprm(&[0x00, 0x08, 0x01, 0x01, 0x08, 0x00]).unwrap();
+        assert!(chp.is_deleted);
+        assert!(!chp.is_inserted);
+
+        let chp = CharacterProperties::from_sprm(&[0x01, 0x08, 0x01]).unwrap();
+        assert!(!chp.is_deleted);
+        assert!(chp.is_inserted);
+    }
+
+    #[test]
+    fn test_language_ids() {
+        // sprmCRgLid0 = en-GB, sprmCRgLid1 = zh-CN, sprmCLidBi = he-IL
+        let chp = CharacterProperties::from_sprm(&[
+            0x73, 0x48, 0x09, 0x08, 0x74, 0x48, 0x04, 0x08, 0x5F, 0x48, 0x0D, 0x04,
+        ])
+        .unwrap();
+        let language = chp.language();
+        assert_eq!(language.latin.as_deref(), Some("en-GB"));
+        assert_eq!(language.east_asian.as_deref(), Some("zh-CN"));
+        assert_eq!(language.complex.as_deref(), Some("he-IL"));
+    }
+
+    #[test]
+    fn test_bidi() {
+        // sprmCFBiDi on, then off
+        let chp = CharacterProperties::from_sprm(&[0x5A, 0x08, 0x01]).unwrap();
+        assert_eq!(chp.is_bidi, Some(true));
+        let chp = CharacterProperties::from_sprm(&[0x5A, 0x08, 0x00]).unwrap();
+        assert_eq!(chp.is_bidi, Some(false));
+        assert_eq!(CharacterProperties::default().is_bidi, None);
+    }
+
     #[test]
     fn test_underline_style() {
         let single = UnderlineStyle::Single;
@@ -10,9 +10,8 @@ use super::chp::CharacterProperties;
 /// - org.apache.poi.hwpf.model.CHPBinTable
 /// - org.apache.poi.hwpf.model.CHPFormattedDiskPage
 /// - [MS-DOC] 2.8.5 PlcfBteChpx
-use super::fkp::ChpxFkp;
+use super::fkp::{ChpxFkp, fkp_page, fkp_pages};
 use super::piece_table::PieceTable;
-use crate::common::binary::read_u32_le;
 
 /// A character run with properties.
 ///
@@ -52,6 +51,23 @@ impl ChpBinTable {
         plcf_bte_chpx_data: &[u8],
         word_document: &[u8],
         piece_table: &PieceTable,
+    ) -> Option<Self> {
+        Self::parse_with_pages(
+            plcf_bte_chpx_data,
+            |pn| fkp_page(word_document, pn),
+            piece_table,
+        )
+    }
+
+    /// Parse CHPBinTable from PlcfBteChpx data, getting each FKP page by
+    /// its page number from `page`.
+    ///
+    /// Pages that `page` doesn't return are skipped, which lets callers
+    /// parse the runs of the pages they have read so far.
+    pub(crate) fn parse_with_pages<'p>(
+        plcf_bte_chpx_data: &[u8],
+        page: impl Fn(u32) -> Option<&'p [u8]>,
+        piece_table: &PieceTable,
     ) -> Option<Self> {
         // PlcfBteChpx structure:
         // - Array of FC positions (4 bytes each, n+1 entries)
@@ -77,41 +93,15 @@ impl ChpBinTable {
         let mut all_runs = Vec::with_capacity(estimated_runs);
 
         // Parse each BTE entry
-        for i in 0..n {
-            // Read PN from PnFkpChpx array (starts after FC array)
-            let pn_offset = (n + 1) * 4 + i * 4;
-            if pn_offset + 4 > plcf_bte_chpx_data.len() {
+        // FKP pages are stored in the WordDocument stream, not table stream!
+        for (_, pn) in fkp_pages(plcf_bte_chpx_data) {
+            // Extract the 512-byte FKP page; pages past the stream are skipped
+            let Some(fkp_page) = page(pn) else {
                 continue;
-            }
-
-            let pn_raw = read_u32_le(plcf_bte_chpx_data, pn_offset).unwrap_or(0);
-
-            // PnFkpChpx structure (from [MS-DOC]):
-            // - Bits 0-21 (22 bits): pn (Page Number)
-            // - Bits 22-31 (10 bits): unused (MUST be ignored)
-            // The ChpxFkp structure begins at offset pn * 512 in WordDocument stream
-            let pn = pn_raw & 0x3FFFFF; // Mask to get 22-bit page number
-
-            // PN (Page Number) format:
-            // pageOffset = 512 * pn
-            // FKP pages are stored in the WordDocument stream, not table stream!
-            if pn == 0 || pn == 0x3FFFFF {
-                // Invalid PN - skip this entry
-                continue;
-            }
-
-            let page_offset = (pn as usize) * 512;
-
-            if page_offset + 512 > word_document.len() {
-                // Page offset exceeds document size - skip
-                continue;
-            }
-
-            // Extract the 512-byte FKP page from WordDocument stream
-            let fkp_page = &word_document[page_offset..page_offset + 512];
+            };
 
             // Parse CHPX FKP
-            if let Some(fkp) = ChpxFkp::parse(fkp_page, word_document) {
+            if let Some(fkp) = ChpxFkp::parse(fkp_page, &[]) {
                 let entry_count = fkp.count();
 
                 // Process entries in batch, caching next FC to avoid redundant lookups
@@ -0,0 +1,148 @@
+/// Document properties (DOP) parser for DOC files.
+///
+/// The DOP holds document-wide settings. Only the editing protection is read:
+/// the Word 97 lock flags of the DopBase and, for files saved by Word 2003
+/// or later, the enforced protection of the Dop2003.
+///
+/// Based on Microsoft's "[MS-DOC]" specification Section 2.7.
+use super::fib::FileInformationBlock;
+use crate::common::binary::read_u32_le;
+use crate::common::{Protection, ProtectionKind};
+
+/// Offset of the DopBase byte holding fLockAtn (0x10).
+const LOCK_ATN_OFFSET: usize = 6;
+/// Offset of the DopBase byte holding fProtEnabled (0x02) and fLockRev (0x40).
+const PROT_FLAGS_OFFSET: usize = 7;
+/// Offset of lKeyProtDoc, the hash of the protection password.
+const KEY_PROT_DOC_OFFSET: usize = 78;
+/// Offset of the Dop2003 byte holding fEnforceDocProt (0x08) and
+/// iDocProtCur (0x70).
+const DOP2003_PROT_OFFSET: usize = 598;
+
+/// Editing protection read from the DOP.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentProperties {
+    data: Vec<u8>,
+}
+
+impl DocumentProperties {
+    /// Parse the DOP from the table stream.
+    ///
+    /// Returns `None` if the document has no DOP.
+    pub fn parse(fib: &FileInformationBlock, table_stream: &[u8]) -> Option<Self> {
+        // Index 31 in FibRgFcLcb97 is fcDop/lcbDop
+        let (offset, length) = fib.get_table_pointer(31)?;
+        let start = offset as usize;
+        let end = start.checked_add(length as usize)?.min(table_stream.len());
+        if length == 0 || start >= end {
+            return None;
+        }
+        Some(Self::from_bytes(&table_stream[start..end]))
+    }
+
+    /// Wrap raw DOP bytes.
+    pub fn from_bytes(data: &[u8]) -> Self {
+        Self {
+            data: data.to_vec(),
+        }
+    }
+
+    #[inline]
+    fn byte(&self, offset: usize) -> u8 {
+        self.data.get(offset).copied().unwrap_or(0)
+    }
+
+    /// Get the editing protection of the document.
+    ///
+    /// The read-only recommendation and the password to modify are FIB
+    /// flags and are not included.
+    pub fn protection(&self) -> Protection {
+        let kind = if self.data.len() > DOP2003_PROT_OFFSET {
+            // Word 2003 and later record the protection type in the Dop2003
+            let flags = self.byte(DOP2003_PROT_OFFSET);
+            if flags & 0x08 == 0 {
+                ProtectionKind::None
+            } else {
+                match (flags >> 4) & 0x07 {
+                    0 => ProtectionKind::TrackedChanges,
+                    1 => ProtectionKind::CommentsOnly,
+                    2 => ProtectionKind::Forms,
+                    3 => ProtectionKind::ReadOnly,
+                    _ => ProtectionKind::None,
+                }
+            }
+        } else {
+            let lock_atn = self.byte(LOCK_ATN_OFFSET) & 0x10 != 0;
+            let flags = self.byte(PROT_FLAGS_OFFSET);
+            if flags & 0x02 != 0 {
+                ProtectionKind::Forms
+            } else if lock_atn {
+                ProtectionKind::CommentsOnly
+            } else if flags & 0x40 != 0 {
+                ProtectionKind::TrackedChanges
+            } else {
+                ProtectionKind::None
+            }
+        };
+
+        let key = read_u32_le(&self.data, KEY_PROT_DOC_OFFSET).unwrap_or(0);
+        Protection {
+            kind,
+            has_password: kind != ProtectionKind::None && key != 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dop97_protection() {
+        let mut data = vec![0u8; 0x1F4];
+        assert_eq!(
+            DocumentProperties::from_bytes(&data).protection(),
+            Protection::default()
+        );
+
+        // fLockAtn with a password hash
+        data[LOCK_ATN_OFFSET] = 0x10;
+        data[KEY_PROT_DOC_OFFSET] = 0x2A;
+        assert_eq!(
+            DocumentProperties::from_bytes(&data).protection(),
+            Protection::with_password(ProtectionKind::CommentsOnly)
+        );
+
+        // fProtEnabled
+        data[PROT_FLAGS_OFFSET] = 0x02;
+        assert_eq!(
+            DocumentProperties::from_bytes(&data).protection().kind,
+            ProtectionKind::Forms
+        );
+    }
+
+    #[test]
+    fn test_dop2003_protection() {
+        let mut data = vec![0u8; 0x29A];
+        // The Word 97 flags are ignored when the Dop2003 is present
+        data[LOCK_ATN_OFFSET] = 0x10;
+        data[DOP2003_PROT_OFFSET] = 0x70;
+        assert_eq!(
+            DocumentProperties::from_bytes(&data).protection(),
+            Protection::default()
+        );
+
+        // fEnforceDocProt with iDocProtCur = 3 (read-only)
+        data[DOP2003_PROT_OFFSET] = 0x08 | (3 << 4);
+        assert_eq!(
+            DocumentProperties::from_bytes(&data).protection().kind,
+            ProtectionKind::ReadOnly
+        );
+
+        data[DOP2003_PROT_OFFSET] = 0x08;
+        assert_eq!(
+            DocumentProperties::from_bytes(&data).protection().kind,
+            ProtectionKind::TrackedChanges
+        );
+    }
+}
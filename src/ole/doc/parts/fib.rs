@@ -7,6 +7,9 @@
 /// - Pointers to various data structures
 /// - Document flags and properties
 use super::super::package::{DocError, Result};
+use crate::common::binary::read_u16_le;
+use crate::ole::OleFile;
+use std::io::{Read, Seek};
 use zerocopy::{FromBytes, LE, U16, U32};
 
 /// Minimum FIB size in bytes (the base FIB structure)
@@ -93,6 +96,45 @@ impl FileInformationBlock {
         })
     }
 
+    /// Read the FIB from the start of the WordDocument stream.
+    ///
+    /// Only the bytes the FIB takes are read, not the whole stream.
+    pub(crate) fn read<R: Read + Seek>(ole: &mut OleFile<R>) -> Result<Self> {
+        let location = ole
+            .stream_location(&["WordDocument"])
+            .map_err(|_| DocError::StreamNotFound("WordDocument".to_string()))?;
+
+        let mut head = Vec::new();
+        loop {
+            let len = (Self::required_len(&head) as u64).min(location.size());
+            if len as usize <= head.len() {
+                break;
+            }
+            head.resize(len as usize, 0);
+            let read = ole.read_stream_range(&location, 0, &mut head)?;
+            head.truncate(read);
+        }
+        Self::parse(&head)
+    }
+
+    /// Get how many bytes at the start of the WordDocument stream the FIB takes.
+    ///
+    /// The FibBase is followed by arrays whose sizes are stored just before
+    /// them (csw, cslw, cbRgFcLcb and cswNew). Given the first bytes of the
+    /// stream, returns the FIB size when `head` holds all of these counts,
+    /// or else how many bytes are needed to read the next one.
+    pub(crate) fn required_len(head: &[u8]) -> usize {
+        let mut len = FIB_BASE_SIZE;
+        // Sizes of the entries counted by csw, cslw, cbRgFcLcb and cswNew
+        for entry_size in [2, 4, 8, 2] {
+            let Ok(count) = read_u16_le(head, len) else {
+                return len + 2;
+            };
+            len += 2 + count as usize * entry_size;
+        }
+        len
+    }
+
     /// Get the file format version.
     ///
     /// Common values:
@@ -146,6 +188,20 @@ impl FileInformationBlock {
         (self.flags & 0x0100) != 0
     }
 
+    /// Check if the document asks to be opened read-only.
+    #[inline]
+    pub fn is_read_only_recommended(&self) -> bool {
+        // fReadOnlyRecommended flag is bit 10 at offset 0x0A
+        (self.flags & 0x0400) != 0
+    }
+
+    /// Check if a password is needed to save changes to the document.
+    #[inline]
+    pub fn is_write_reserved(&self) -> bool {
+        // fWriteReservation flag is bit 11 at offset 0x0A
+        (self.flags & 0x0800) != 0
+    }
+
     /// Get the language ID.
     #[inline]
     pub fn language_id(&self) -> u16 {
@@ -232,100 +288,59 @@ impl FileInformationBlock {
         (0, ccp_text)
     }
 
+    /// Get the character position range of the subdocument whose count is at `index`.
+    ///
+    /// Subdocuments follow each other in the text stream, so the range starts
+    /// after the counts of all earlier ones. Counts read from a malformed FIB
+    /// saturate instead of overflowing.
+    fn get_subdoc_range(&self, index: usize) -> Option<(u32, u32)> {
+        let base = (0..index).fold(0u32, |sum, i| {
+            sum.saturating_add(self.get_character_count(i))
+        });
+        let count = self.get_character_count(index);
+        (count > 0).then(|| (base, base.saturating_add(count)))
+    }
+
     /// Get the footnote subdocument character position range.
     ///
     /// Returns Some((start_cp, end_cp)) if footnotes exist, None otherwise.
     pub fn get_footnote_range(&self) -> Option<(u32, u32)> {
-        let base = self.get_character_count(0);
-        let ccp_ftn = self.get_character_count(1);
-        if ccp_ftn > 0 {
-            Some((base, base + ccp_ftn))
-        } else {
-            None
-        }
+        self.get_subdoc_range(1)
     }
 
     /// Get the header/footer subdocument character position range.
     ///
     /// Returns Some((start_cp, end_cp)) if headers/footers exist, None otherwise.
     pub fn get_header_range(&self) -> Option<(u32, u32)> {
-        let base = self.get_character_count(0) + self.get_character_count(1);
-        let ccp_hdd = self.get_character_count(2);
-        if ccp_hdd > 0 {
-            Some((base, base + ccp_hdd))
-        } else {
-            None
-        }
+        self.get_subdoc_range(2)
     }
 
     /// Get the annotations/comments subdocument character position range.
     ///
     /// Returns Some((start_cp, end_cp)) if comments exist, None otherwise.
     pub fn get_comment_range(&self) -> Option<(u32, u32)> {
-        let base = self.get_character_count(0)
-            + self.get_character_count(1)
-            + self.get_character_count(2)
-            + self.get_character_count(3); // Skip macros
-        let ccp_atn = self.get_character_count(4);
-        if ccp_atn > 0 {
-            Some((base, base + ccp_atn))
-        } else {
-            None
-        }
+        self.get_subdoc_range(4)
     }
 
     /// Get the endnotes subdocument character position range.
     ///
     /// Returns Some((start_cp, end_cp)) if endnotes exist, None otherwise.
     pub fn get_endnote_range(&self) -> Option<(u32, u32)> {
-        let base = self.get_character_count(0)
-            + self.get_character_count(1)
-            + self.get_character_count(2)
-            + self.get_character_count(3)
-            + self.get_character_count(4);
-        let ccp_edn = self.get_character_count(5);
-        if ccp_edn > 0 {
-            Some((base, base + ccp_edn))
-        } else {
-            None
-        }
+        self.get_subdoc_range(5)
     }
 
     /// Get the text box subdocument character position range.
     ///
     /// Returns Some((start_cp, end_cp)) if text boxes exist, None otherwise.
     pub fn get_textbox_range(&self) -> Option<(u32, u32)> {
-        let base = self.get_character_count(0)
-            + self.get_character_count(1)
-            + self.get_character_count(2)
-            + self.get_character_count(3)
-            + self.get_character_count(4)
-            + self.get_character_count(5);
-        let ccp_txbx = self.get_character_count(6);
-        if ccp_txbx > 0 {
-            Some((base, base + ccp_txbx))
-        } else {
-            None
-        }
+        self.get_subdoc_range(6)
     }
 
     /// Get the header text box subdocument character position range.
     ///
     /// Returns Some((start_cp, end_cp)) if header text boxes exist, None otherwise.
     pub fn get_header_textbox_range(&self) -> Option<(u32, u32)> {
-        let base = self.get_character_count(0)
-            + self.get_character_count(1)
-            + self.get_character_count(2)
-            + self.get_character_count(3)
-            + self.get_character_count(4)
-            + self.get_character_count(5)
-            + self.get_character_count(6);
-        let ccp_hdr_txbx = self.get_character_count(7);
-        if ccp_hdr_txbx > 0 {
-            Some((base, base + ccp_hdr_txbx))
-        } else {
-            None
-        }
+        self.get_subdoc_range(7)
     }
 
     /// Get all subdocument ranges that exist in this document.
@@ -419,4 +434,20 @@ mod tests {
         let fib = FileInformationBlock::parse(&data).unwrap();
         assert!(fib.which_table_stream());
     }
+
+    #[test]
+    fn test_fib_write_protection_flags() {
+        let mut data = vec![0u8; 512];
+        data[0] = 0xEC;
+        data[1] = 0xA5;
+        let fib = FileInformationBlock::parse(&data).unwrap();
+        assert!(!fib.is_read_only_recommended());
+        assert!(!fib.is_write_reserved());
+
+        // fReadOnlyRecommended (bit 10) and fWriteReservation (bit 11)
+        data[11] = 0x0C;
+        let fib = FileInformationBlock::parse(&data).unwrap();
+        assert!(fib.is_read_only_recommended());
+        assert!(fib.is_write_reserved());
+    }
 }
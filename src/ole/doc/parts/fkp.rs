@@ -310,7 +310,7 @@ impl PapxFkp {
     ///
     /// The size encoding is complex:
     /// - If first byte is 0: next byte contains size (in words)
-    /// - Otherwise: first byte contains size (in words), subtract 1
+    /// - Otherwise: first byte contains size (in words), minus one byte
     ///
     /// Based on POI's PAPFormattedDiskPage.getGrpprl()
     #[inline]
@@ -320,21 +320,18 @@ impl PapxFkp {
         }
 
         let first_byte = page_data[papx_offset] as usize;
-        let (size_in_words, grpprl_start) = if first_byte == 0 {
+        let (size_in_bytes, grpprl_start) = if first_byte == 0 {
             // Size is in next byte
             if papx_offset + 1 >= page_data.len() {
                 return None;
             }
             let size = page_data[papx_offset + 1] as usize;
-            (size, papx_offset + 2)
+            (size * 2, papx_offset + 2)
         } else {
-            // Size is in first byte, but subtract 1
-            (first_byte - 1, papx_offset + 1)
+            // Size is in first byte: 2 * cb - 1 bytes
+            (first_byte * 2 - 1, papx_offset + 1)
         };
 
-        // Convert size from words to bytes
-        let size_in_bytes = size_in_words * 2;
-
         // Validate bounds
         let grpprl_end = grpprl_start + size_in_bytes;
         if grpprl_end > FKP_PAGE_SIZE {
@@ -359,6 +356,34 @@ impl PapxFkp {
     }
 }
 
+/// List the FKP pages of a PlcfBteChpx or PlcfBtePapx.
+///
+/// Both hold (n+1) FCs followed by n PnFkp* entries, whose low 22 bits are
+/// the page number of an FKP in the WordDocument stream (at `pn * 512`).
+/// Returns the first FC and page number of each entry with a valid page number.
+pub(crate) fn fkp_pages(plcf_bte: &[u8]) -> Vec<(u32, u32)> {
+    if plcf_bte.len() < 8 {
+        return Vec::new();
+    }
+
+    let n = (plcf_bte.len() - 4) / 8;
+    (0..n)
+        .filter_map(|i| {
+            let fc = read_u32_le(plcf_bte, i * FC_SIZE).ok()?;
+            let pn = read_u32_le(plcf_bte, (n + 1) * FC_SIZE + i * 4).ok()? & 0x3FFFFF;
+            // 0 and 0x3FFFFF are not valid page numbers
+            (pn != 0 && pn != 0x3FFFFF).then_some((fc, pn))
+        })
+        .collect()
+}
+
+/// Get the FKP page with page number `pn` from the WordDocument stream.
+#[inline]
+pub(crate) fn fkp_page(word_document: &[u8], pn: u32) -> Option<&[u8]> {
+    let offset = (pn as usize) * FKP_PAGE_SIZE;
+    word_document.get(offset..offset + FKP_PAGE_SIZE)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -482,9 +507,9 @@ mod tests {
         page[17..21].copy_from_slice(&360i32.to_le_bytes()); // dym_line_or_height
 
         // Place grpprl data at offset 200
-        // Size encoding: if first byte != 0, size = (first_byte - 1) * 2
-        page[200] = 3; // size = (3-1)*2 = 4 bytes
-        page[201..205].copy_from_slice(&[0x01, 0x02, 0x03, 0x04]); // grpprl data
+        // Size encoding: if first byte != 0, size = first_byte * 2 - 1
+        page[200] = 3; // size = 3*2-1 = 5 bytes
+        page[201..206].copy_from_slice(&[0x01, 0x02, 0x03, 0x04, 0x05]); // grpprl data
 
         let fkp = PapxFkp::parse(&page, &[]).expect("Failed to parse PAPX FKP");
 
@@ -492,7 +517,7 @@ mod tests {
 
         let entry = fkp.entry(0).expect("Failed to get entry 0");
         assert_eq!(entry.fc, 0);
-        assert_eq!(entry.grpprl.len(), 4);
+        assert_eq!(entry.grpprl, [0x01, 0x02, 0x03, 0x04, 0x05]);
 
         let phe = entry
             .paragraph_height
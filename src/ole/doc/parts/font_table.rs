@@ -0,0 +1,164 @@
+/// Font table (SttbfFfn) parser for Word binary format.
+///
+/// Based on Apache POI's FontTable and Ffn classes. Only the font name and
+/// character set are kept, which is what text decoding needs.
+///
+/// References:
+/// - org.apache.poi.hwpf.model.FontTable
+/// - [MS-DOC] 2.9.281 SttbfFfn, 2.9.82 FFN
+use super::fib::FileInformationBlock;
+use crate::common::binary::read_u16_le;
+use crate::common::encoding::charset_to_codepage;
+
+/// Offset of the font name (xszFfn) within an FFN
+const FFN_NAME_OFFSET: usize = 40;
+
+/// `SYMBOL_CHARSET`
+const SYMBOL_CHARSET: u8 = 2;
+
+/// A font table entry (FFN).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FontEntry {
+    /// Font name
+    pub name: String,
+    /// Character set (chs), as in `LOGFONT.lfCharSet`
+    pub charset: u8,
+}
+
+impl FontEntry {
+    /// Check whether the font draws symbols rather than letters.
+    pub fn is_symbol(&self) -> bool {
+        self.charset == SYMBOL_CHARSET
+            || self.name.eq_ignore_ascii_case("Symbol")
+            || self.name.to_ascii_lowercase().starts_with("wingdings")
+    }
+
+    /// Get the codepage of 8-bit text in this font.
+    ///
+    /// Returns `None` for fonts that follow the document codepage: the
+    /// default and symbol character sets, and ANSI fonts, which Word also
+    /// uses for text of other languages.
+    pub fn codepage(&self) -> Option<u32> {
+        match self.charset {
+            0 | 1 | SYMBOL_CHARSET => None,
+            charset => charset_to_codepage(charset),
+        }
+    }
+}
+
+/// The document font table, indexed by ftc.
+#[derive(Debug, Clone, Default)]
+pub struct FontTable {
+    fonts: Vec<FontEntry>,
+}
+
+impl FontTable {
+    /// Parse the font table referenced by the FIB.
+    ///
+    /// # Arguments
+    ///
+    /// * `fib` - File Information Block
+    /// * `table_stream` - Table stream (0Table or 1Table) data
+    pub fn parse(fib: &FileInformationBlock, table_stream: &[u8]) -> Option<Self> {
+        // Index 15 in FibRgFcLcb97 is fcSttbfFfn/lcbSttbfFfn
+        let (offset, length) = fib.get_table_pointer(15)?;
+        let start = offset as usize;
+        let end = start.checked_add(length as usize)?.min(table_stream.len());
+        if length == 0 || start >= end {
+            return None;
+        }
+        Some(Self::from_bytes(&table_stream[start..end]))
+    }
+
+    /// Parse a font table from raw SttbfFfn bytes.
+    ///
+    /// Truncated entries end the table; the fonts read so far are kept.
+    pub fn from_bytes(data: &[u8]) -> Self {
+        let count = read_u16_le(data, 0).unwrap_or(0) as usize;
+        // cbExtra (u16) follows the count and is always 0
+        let mut offset = 4;
+        let mut fonts = Vec::with_capacity(count.min(data.len() / FFN_NAME_OFFSET));
+
+        for _ in 0..count {
+            let Some(&cb) = data.get(offset) else {
+                break;
+            };
+            let ffn_end = offset + cb as usize + 1;
+            let Some(ffn) = data.get(offset..ffn_end) else {
+                break;
+            };
+            if ffn.len() < FFN_NAME_OFFSET {
+                break;
+            }
+
+            let name: Vec<u16> = ffn[FFN_NAME_OFFSET..]
+                .chunks_exact(2)
+                .map(|unit| u16::from_le_bytes([unit[0], unit[1]]))
+                .take_while(|&unit| unit != 0)
+                .collect();
+            fonts.push(FontEntry {
+                name: String::from_utf16_lossy(&name),
+                charset: ffn[4],
+            });
+            offset = ffn_end;
+        }
+
+        Self { fonts }
+    }
+
+    /// Get a font by its index (ftc).
+    #[inline]
+    pub fn get(&self, ftc: u16) -> Option<&FontEntry> {
+        self.fonts.get(ftc as usize)
+    }
+
+    /// Get the number of fonts.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.fonts.len()
+    }
+
+    /// Check whether the table has no fonts.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.fonts.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build an FFN with the given charset and name
+    fn ffn(name: &str, charset: u8) -> Vec<u8> {
+        let mut data = vec![0u8; FFN_NAME_OFFSET];
+        data[4] = charset;
+        for unit in name.encode_utf16().chain([0]) {
+            data.extend_from_slice(&unit.to_le_bytes());
+        }
+        data[0] = (data.len() - 1) as u8;
+        data
+    }
+
+    #[test]
+    fn test_parse_font_table() {
+        let mut data = vec![3, 0, 0, 0];
+        data.extend(ffn("Times New Roman Cyr", 204));
+        data.extend(ffn("Symbol", 2));
+        data.extend(ffn("Arial", 0));
+
+        let table = FontTable::from_bytes(&data);
+        assert_eq!(table.len(), 3);
+        let cyr = table.get(0).unwrap();
+        assert_eq!(cyr.name, "Times New Roman Cyr");
+        assert_eq!(cyr.codepage(), Some(1251));
+        assert!(!cyr.is_symbol());
+        assert!(table.get(1).unwrap().is_symbol());
+        assert_eq!(table.get(2).unwrap().codepage(), None);
+        assert!(table.get(3).is_none());
+
+        // A truncated entry keeps the fonts before it
+        let table = FontTable::from_bytes(&data[..data.len() - 4]);
+        assert_eq!(table.len(), 2);
+    }
+}
@@ -52,21 +52,37 @@ impl HeaderFooterType {
     }
 }
 
+/// Number of separator stories preceding the section stories in plcfHdd
+const SEPARATOR_STORIES: usize = 6;
+
+/// Story types of each section, in plcfHdd order
+const SECTION_STORIES: [HeaderFooterType; 6] = [
+    HeaderFooterType::EvenPageHeader,
+    HeaderFooterType::OddPageHeader,
+    HeaderFooterType::EvenPageFooter,
+    HeaderFooterType::OddPageFooter,
+    HeaderFooterType::FirstPageHeader,
+    HeaderFooterType::FirstPageFooter,
+];
+
 /// A header or footer story (text content)
 #[derive(Debug, Clone)]
 pub struct HeaderFooterStory {
     /// Type of header/footer
     pub story_type: HeaderFooterType,
+    /// Index of the section the story belongs to (0-based)
+    pub section_index: usize,
     /// Character position range in the header subdocument
     pub start_cp: u32,
     pub end_cp: u32,
 }
 
 impl HeaderFooterStory {
-    /// Create a new header/footer story
+    /// Create a new header/footer story of the first section
     pub fn new(story_type: HeaderFooterType, start_cp: u32, end_cp: u32) -> Self {
         Self {
             story_type,
+            section_index: 0,
             start_cp,
             end_cp,
         }
@@ -110,8 +126,8 @@ impl HeadersTable {
         // Check if headers/footers exist
         if let Some((start_cp, end_cp)) = fib.get_header_range() {
             // Get the PLCF for headers/footers (plcfHdd)
-            // FIB index 9: fcPlcfHdd and lcbPlcfHdd
-            if let Some((offset, length)) = fib.get_table_pointer(9)
+            // FIB index 11: fcPlcfHdd and lcbPlcfHdd (FIB offset 0x00F2)
+            if let Some((offset, length)) = fib.get_table_pointer(11)
                 && length > 0
                 && (offset as usize) < table_stream.len()
             {
@@ -131,7 +147,11 @@ impl HeadersTable {
     ///
     /// The plcfHdd PLCF has element_size = 0 (just character positions).
     /// It contains character positions that divide the header subdocument into stories.
-    /// Each section can have up to 6 stories (first page header/footer, even page, odd page).
+    /// The first six stories are the footnote and endnote separators and
+    /// continuation notices; they are followed by six stories per section in
+    /// the order even header, odd header, even footer, odd footer, first
+    /// header and first footer ([MS-DOC] Plcfhdd). Any trailing
+    /// CPs that do not make up a whole section are ignored.
     fn parse_header_plcf(
         data: &[u8],
         subdoc_start: u32,
@@ -161,22 +181,25 @@ impl HeadersTable {
         // Build stories from consecutive CP pairs
         // Each pair of CPs defines one header/footer story
         let mut stories = Vec::new();
-        let header_types = HeaderFooterType::all_types();
+        let section_count =
+            (cps.len() - 1).saturating_sub(SEPARATOR_STORIES) / SECTION_STORIES.len();
 
-        for i in 0..(cps.len() - 1) {
+        for i in SEPARATOR_STORIES..SEPARATOR_STORIES + section_count * SECTION_STORIES.len() {
             let start = cps[i];
             let end = cps[i + 1];
 
             // Convert relative CPs to absolute CPs in the text stream
-            let abs_start = subdoc_start + start;
-            let abs_end = subdoc_start + end;
-
-            // Determine story type based on position
-            // Each section contributes up to 6 stories in order
-            let type_index = i % header_types.len();
-            let story_type = header_types[type_index];
-
-            stories.push(HeaderFooterStory::new(story_type, abs_start, abs_end));
+            let abs_start = subdoc_start.saturating_add(start);
+            let abs_end = subdoc_start.saturating_add(end);
+
+            let position = i - SEPARATOR_STORIES;
+            let mut story = HeaderFooterStory::new(
+                SECTION_STORIES[position % SECTION_STORIES.len()],
+                abs_start,
+                abs_end,
+            );
+            story.section_index = position / SECTION_STORIES.len();
+            stories.push(story);
         }
 
         stories
@@ -274,6 +297,22 @@ mod tests {
         assert!(!HeaderFooterType::OddPageHeader.is_footer());
     }
 
+    #[test]
+    fn test_parse_header_plcf() {
+        // Six separator stories, one section and a trailing guard CP
+        let cps: [u32; 14] = [0, 2, 4, 6, 8, 10, 12, 12, 20, 20, 31, 31, 31, 33];
+        let data: Vec<u8> = cps.iter().flat_map(|cp| cp.to_le_bytes()).collect();
+        let stories = HeadersTable::parse_header_plcf(&data, 100, 133);
+
+        assert_eq!(stories.len(), 6);
+        assert!(stories.iter().all(|story| story.section_index == 0));
+        assert_eq!(stories[1].story_type, HeaderFooterType::OddPageHeader);
+        assert_eq!((stories[1].start_cp, stories[1].end_cp), (112, 120));
+        assert_eq!(stories[3].story_type, HeaderFooterType::OddPageFooter);
+        assert_eq!(stories[3].length(), 11);
+        assert!(stories[0].is_empty());
+    }
+
     #[test]
     fn test_header_footer_story() {
         let story = HeaderFooterStory::new(HeaderFooterType::OddPageHeader, 100, 200);
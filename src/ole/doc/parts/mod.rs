@@ -8,18 +8,27 @@
 /// - Style definitions
 /// - Table structures
 /// - Headers/footers, footnotes/endnotes, hyperlinks, numbering/lists
+/// - Text boxes
+/// - Section page setup
+/// - Document editing protection
 pub mod chp;
 pub mod chp_bin_table;
+pub mod dop;
 pub mod fib;
 pub mod fields;
 pub mod fkp;
+pub mod font_table;
 pub mod footnotes;
 pub mod headers;
 pub mod hyperlinks;
 pub mod numbering;
 pub mod pap;
+pub mod pap_bin_table;
 pub mod paragraph_extractor;
 pub mod piece_table;
+pub mod sections;
+pub mod stylesheet;
 pub mod tap;
 pub mod tap_parser;
 pub mod text;
+pub mod textboxes;
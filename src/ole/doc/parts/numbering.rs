@@ -8,6 +8,7 @@
 use super::super::package::{DocError, Result};
 use super::fib::FileInformationBlock;
 use crate::common::binary;
+use crate::document::{ListLevelFormat, NumberStyle};
 
 /// Number format for list levels
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -90,7 +91,8 @@ pub struct ListLevel {
     pub indent_left: i32,
     /// Hanging indent in twips
     pub indent_hanging: i32,
-    /// Number text (format string with placeholders)
+    /// Number text (format string with placeholders); characters
+    /// U+0000-U+0008 stand for the counters of levels 0-8
     pub number_text: String,
 }
 
@@ -105,7 +107,8 @@ impl ListLevel {
             .map_err(|e| DocError::InvalidFormat(format!("Failed to read start_at: {}", e)))?;
         let number_format = NumberFormat::from_u8(data[4]);
         let alignment = ListAlignment::from_u8(data[5]);
-        let follow_char = data[7];
+        // ixchFollow at offset 15, after the 9-byte rgbxchNums
+        let follow_char = data[15];
 
         // Read indentation values (signed 32-bit)
         let indent_left = binary::read_i32_le(data, 12)
@@ -114,26 +117,13 @@ impl ListLevel {
             DocError::InvalidFormat(format!("Failed to read indent_hanging: {}", e))
         })?;
 
-        // Number text follows the fixed structure
-        let number_text = if data.len() > 28 {
-            // Read cbGrpprlChpx and cbGrpprlPapx to skip SPRM data
-            let cb_chpx = data.get(25).copied().unwrap_or(0) as usize;
-            let cb_papx = data.get(26).copied().unwrap_or(0) as usize;
-
-            // Number text length is at offset 27
-            let text_len = data.get(27).copied().unwrap_or(0) as usize;
-            let text_offset = 28 + cb_chpx + cb_papx;
-
-            if text_offset + text_len * 2 <= data.len() {
-                // Number text is UTF-16LE
-                let text_bytes = &data[text_offset..text_offset + text_len * 2];
-                <String as Utf16LeExt>::from_utf16le_lossy(text_bytes)
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        };
+        // The number text (xst) follows grpprlPapx and grpprlChpx
+        let text_offset = Self::text_offset(data);
+        let number_text = binary::read_u16_le(data, text_offset)
+            .ok()
+            .and_then(|cch| data.get(text_offset + 2..text_offset + 2 + cch as usize * 2))
+            .map(<String as Utf16LeExt>::from_utf16le_lossy)
+            .unwrap_or_default();
 
         Ok(Self {
             start_at,
@@ -147,6 +137,21 @@ impl ListLevel {
         })
     }
 
+    /// Offset of the xst in an LVL, after the LVLF and its grpprls.
+    fn text_offset(data: &[u8]) -> usize {
+        // cbGrpprlChpx at offset 24, cbGrpprlPapx at offset 25
+        let cb_chpx = data.get(24).copied().unwrap_or(0) as usize;
+        let cb_papx = data.get(25).copied().unwrap_or(0) as usize;
+        28 + cb_papx + cb_chpx
+    }
+
+    /// Size in bytes of the LVL structure starting at `data`.
+    fn byte_len(data: &[u8]) -> usize {
+        let text_offset = Self::text_offset(data);
+        let cch = binary::read_u16_le(data, text_offset).unwrap_or(0) as usize;
+        text_offset + 2 + cch * 2
+    }
+
     /// Check if this is a bullet list
     pub fn is_bullet(&self) -> bool {
         self.number_format == NumberFormat::Bullet
@@ -172,7 +177,9 @@ pub struct ListStructure {
 }
 
 impl ListStructure {
-    /// Parse a list structure from LST
+    /// Parse a list structure from its LSTF.
+    ///
+    /// The levels are stored apart from the LSTF and are added by [`ListTables`].
     pub fn from_bytes(data: &[u8]) -> Result<Self> {
         if data.len() < 28 {
             return Err(DocError::InvalidFormat("LST too short".to_string()));
@@ -187,35 +194,11 @@ impl ListStructure {
         let flags = data[26];
         let is_simple = (flags & 0x01) != 0;
 
-        // Parse levels (LVL structures follow LST)
-        let mut levels = Vec::new();
-        let mut offset = 28;
-
-        for level in 0..9 {
-            if offset + 28 <= data.len() {
-                if let Ok(lvl) = ListLevel::from_bytes(&data[offset..], level) {
-                    levels.push(lvl);
-
-                    // Calculate actual LVLF size to advance offset
-                    // This is approximate - in reality we need to parse cbGrpprlChpx, cbGrpprlPapx
-                    let cb_chpx = data.get(offset + 25).copied().unwrap_or(0) as usize;
-                    let cb_papx = data.get(offset + 26).copied().unwrap_or(0) as usize;
-                    let text_len = data.get(offset + 27).copied().unwrap_or(0) as usize;
-
-                    offset += 28 + cb_chpx + cb_papx + text_len * 2;
-                } else {
-                    break;
-                }
-            } else {
-                break;
-            }
-        }
-
         Ok(Self {
             list_id,
             template_id,
             is_simple,
-            levels,
+            levels: Vec::new(),
         })
     }
 
@@ -223,6 +206,34 @@ impl ListStructure {
     pub fn level(&self, level: u8) -> Option<&ListLevel> {
         self.levels.get(level as usize)
     }
+
+    /// Get the level formats used to compute item labels.
+    pub(crate) fn level_formats(&self) -> Vec<ListLevelFormat> {
+        self.levels
+            .iter()
+            .map(|level| ListLevelFormat {
+                start: level.start_at,
+                style: match level.number_format {
+                    NumberFormat::UpperRoman => NumberStyle::UpperRoman,
+                    NumberFormat::LowerRoman => NumberStyle::LowerRoman,
+                    NumberFormat::UpperLetter => NumberStyle::UpperLetter,
+                    NumberFormat::LowerLetter => NumberStyle::LowerLetter,
+                    NumberFormat::Bullet => NumberStyle::Bullet,
+                    NumberFormat::None => NumberStyle::None,
+                    _ => NumberStyle::Decimal,
+                },
+                // Placeholder characters 0-8 become "%1"-"%9"
+                text: level
+                    .number_text
+                    .chars()
+                    .map(|c| match c as u32 {
+                        n @ 0..=8 => format!("%{}", n + 1),
+                        _ => c.to_string(),
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
 }
 
 /// List Format Override (LFO structure)
@@ -276,19 +287,17 @@ impl ListTables {
         let mut list_structures = Vec::new();
         let mut list_overrides = Vec::new();
 
-        // Parse PlfLst (List Table) - FIB index 27
-        if let Some((offset, length)) = fib.get_table_pointer(27)
+        // Parse PlfLst (List Table) - FIB index 73 (fcPlfLst)
+        if let Some((offset, length)) = fib.get_table_pointer(73)
             && length > 0
             && (offset as usize) < table_stream.len()
         {
-            let plf_data = &table_stream[offset as usize..];
-            let plf_len = length.min((table_stream.len() - offset as usize) as u32) as usize;
-
-            list_structures = Self::parse_plflst(&plf_data[..plf_len])?;
+            // The LVLs follow the PlfLst and are not counted in lcbPlfLst
+            list_structures = Self::parse_plflst(&table_stream[offset as usize..])?;
         }
 
-        // Parse PlfLfo (List Format Override Table) - FIB index 28
-        if let Some((offset, length)) = fib.get_table_pointer(28)
+        // Parse PlfLfo (List Format Override Table) - FIB index 74 (fcPlfLfo)
+        if let Some((offset, length)) = fib.get_table_pointer(74)
             && length > 0
             && (offset as usize) < table_stream.len()
         {
@@ -304,8 +313,13 @@ impl ListTables {
         })
     }
 
-    /// Parse PlfLst (List Table)
+    /// Parse PlfLst (List Table) and the LVLs that follow it.
+    ///
+    /// The PlfLst holds `cLst` LSTFs of 28 bytes. The LVLs of every list come
+    /// right after them, in the same order: one for simple lists, nine otherwise.
     fn parse_plflst(data: &[u8]) -> Result<Vec<ListStructure>> {
+        const LSTF_SIZE: usize = 28;
+
         if data.len() < 2 {
             return Ok(Vec::new());
         }
@@ -313,28 +327,27 @@ impl ListTables {
         let count = binary::read_u16_le(data, 0)
             .map_err(|e| DocError::InvalidFormat(format!("Failed to read count: {}", e)))?
             as usize;
+        let count = count.min((data.len() - 2) / LSTF_SIZE);
         let mut structures = Vec::with_capacity(count);
-        let mut offset = 2;
+        let mut level_offset = 2 + count * LSTF_SIZE;
 
-        for _ in 0..count {
-            if offset >= data.len() {
-                break;
-            }
+        for i in 0..count {
+            let offset = 2 + i * LSTF_SIZE;
+            let mut lst = ListStructure::from_bytes(&data[offset..offset + LSTF_SIZE])?;
 
-            // Each LST is variable length, parse and advance
-            if let Ok(lst) = ListStructure::from_bytes(&data[offset..]) {
-                // Calculate size (this is approximate)
-                let mut lst_size = 28;
-                for _level in &lst.levels {
-                    lst_size += 28; // Base LVLF
-                    // Add SPRM and text sizes (simplified)
-                }
-
-                structures.push(lst);
-                offset += lst_size.min(data.len() - offset);
-            } else {
-                break;
+            let level_count = if lst.is_simple { 1 } else { 9 };
+            for level in 0..level_count {
+                let Some(lvl_data) = data.get(level_offset..) else {
+                    break;
+                };
+                let Ok(lvl) = ListLevel::from_bytes(lvl_data, level) else {
+                    break;
+                };
+                level_offset += ListLevel::byte_len(lvl_data);
+                lst.levels.push(lvl);
             }
+
+            structures.push(lst);
         }
 
         Ok(structures)
@@ -346,20 +359,20 @@ impl ListTables {
             return Ok(Vec::new());
         }
 
+        // Each LFO is 16 bytes; the LFOData entries after them are not needed
+        const LFO_SIZE: usize = 16;
+
         let count = binary::read_u32_le(data, 0)
             .map_err(|e| DocError::InvalidFormat(format!("Failed to read count: {}", e)))?
             as usize;
+        let count = count.min((data.len() - 4) / LFO_SIZE);
         let mut overrides = Vec::with_capacity(count);
         let mut offset = 4;
 
         for _ in 0..count {
-            if offset + 12 > data.len() {
-                break;
-            }
-
             if let Ok(lfo) = ListFormatOverride::from_bytes(&data[offset..]) {
                 overrides.push(lfo);
-                offset += 12;
+                offset += LFO_SIZE;
             } else {
                 break;
             }
@@ -951,25 +964,62 @@ mod tests {
         data[0] = 1; // start_at
         data[4] = 0; // Arabic
         data[5] = 0; // Left
-        data[7] = 0; // follow_char
-        // cbGrpprlChpx at offset 25
+        data[15] = 1; // follow_char (space)
+        // cbGrpprlChpx at offset 24
+        data[24] = 2;
+        // cbGrpprlPapx at offset 25
         data[25] = 0;
-        // cbGrpprlPapx at offset 26
-        data[26] = 0;
-        // text length at offset 27
-        data[27] = 3; // 3 characters
-
-        // Add UTF-16LE text at offset 28: "%1."
-        let text = "%1.";
-        let text_offset = 28;
-        for (i, c) in text.encode_utf16().enumerate() {
-            let bytes = c.to_le_bytes();
-            data[text_offset + i * 2] = bytes[0];
-            data[text_offset + i * 2 + 1] = bytes[1];
-        }
+
+        // Number text after the grpprls: cch, then the level 0 placeholder and "."
+        let text_offset = 28 + 2;
+        data[text_offset] = 2;
+        data[text_offset + 4] = b'.';
 
         let level = ListLevel::from_bytes(&data, 0).unwrap();
-        assert_eq!(level.number_text, "%1.");
+        assert_eq!(level.follow_char, 1);
+        assert_eq!(level.number_text, "\u{0}.");
+        assert_eq!(ListLevel::byte_len(&data), 36);
+    }
+
+    #[test]
+    fn test_list_tables_parse_plflst_levels() {
+        let lstf = |list_id: u32, simple: bool| {
+            let mut data = vec![0u8; 28];
+            data[..4].copy_from_slice(&list_id.to_le_bytes());
+            data[26] = simple as u8;
+            data
+        };
+        let lvl = |nfc: u8, text: &[u16]| {
+            let mut data = vec![0u8; 28];
+            data[0] = 1;
+            data[4] = nfc;
+            data.extend_from_slice(&(text.len() as u16).to_le_bytes());
+            for ch in text {
+                data.extend_from_slice(&ch.to_le_bytes());
+            }
+            data
+        };
+
+        let mut data = 2u16.to_le_bytes().to_vec();
+        data.extend(lstf(10, true));
+        data.extend(lstf(20, false));
+        // LVLs follow all LSTFs: one for the simple list, nine for the other
+        data.extend(lvl(23, &[0x2022]));
+        for level in 0..9u16 {
+            data.extend(lvl(4, &[level, u16::from(b')')]));
+        }
+
+        let structures = ListTables::parse_plflst(&data).unwrap();
+        assert_eq!(structures.len(), 2);
+        assert_eq!(structures[0].list_id, 10);
+        assert_eq!(structures[0].levels.len(), 1);
+        assert!(structures[0].levels[0].is_bullet());
+        assert_eq!(structures[1].levels.len(), 9);
+        assert_eq!(structures[1].levels[8].number_text, "\u{8})");
+        assert_eq!(
+            structures[1].levels[8].number_format,
+            NumberFormat::LowerLetter
+        );
     }
 
     #[test]
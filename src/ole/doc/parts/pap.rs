@@ -10,9 +10,12 @@
 ///
 /// Based on Apache POI's ParagraphSprmUncompressor and ParagraphProperties.
 use super::super::package::Result;
+use super::tap::TableProperties;
+use super::tap_parser::TapParser;
 use crate::common::binary::{read_i16_le, read_u16_le, read_u32_le};
 use crate::ole::sprm::{Sprm, parse_sprms};
 use crate::ole::sprm_operations::*;
+use bumpalo::Bump;
 
 /// Paragraph Properties structure.
 ///
@@ -108,6 +111,10 @@ pub struct ParagraphProperties {
     pub dxa_from_text: Option<i16>,
     /// Vertical distance from text
     pub dya_from_text: Option<i16>,
+    /// Paragraph ends with a cell mark (last paragraph of a cell or row end)
+    pub ends_cell: bool,
+    /// Table row definition, present on table row end paragraphs
+    pub table_properties: Option<Box<TableProperties>>,
 }
 
 /// Paragraph justification/alignment.
@@ -294,6 +301,17 @@ impl ParagraphProperties {
             }
         }
 
+        // Word 97 sets only fInTable; such paragraphs are in a top-level table
+        if pap.in_table && pap.table_nesting_level == 0 {
+            pap.table_nesting_level = 1;
+        }
+
+        // Row end paragraphs also carry the TAP of their row
+        if pap.is_table_row_end || pap.inner_table_row_end {
+            let arena = Bump::new();
+            pap.table_properties = Some(Box::new(TapParser::new(&arena).parse_tap(grpprl)?));
+        }
+
         Ok(pap)
     }
 
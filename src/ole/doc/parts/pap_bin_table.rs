@@ -0,0 +1,140 @@
+/// PAPBinTable (Paragraph Property Bin Table) parser.
+///
+/// Based on Apache POI's PAPBinTable class.
+/// Mirrors [`ChpBinTable`](super::chp_bin_table::ChpBinTable): PlcfBtePapx
+/// holds page numbers of PAPXFKP pages, which carry the style index (istd)
+/// and grpprl of each paragraph.
+///
+/// References:
+/// - org.apache.poi.hwpf.model.PAPBinTable
+/// - org.apache.poi.hwpf.model.PAPFormattedDiskPage
+/// - [MS-DOC] 2.8.6 PlcfBtePapx
+use super::fkp::{PapxFkp, fkp_page, fkp_pages};
+use super::pap::ParagraphProperties;
+use super::piece_table::PieceTable;
+use crate::common::binary::{read_u16_le, read_u32_le};
+
+/// A range of paragraph marks sharing the same paragraph properties.
+#[derive(Debug, Clone)]
+pub struct ParagraphRun {
+    /// Start character position
+    pub start_cp: u32,
+    /// End character position
+    pub end_cp: u32,
+    /// Paragraph properties, with `style_index` set from the PAPX istd
+    pub properties: ParagraphProperties,
+}
+
+/// PAPBinTable - manages paragraph property bin table.
+#[derive(Debug)]
+pub struct PapBinTable {
+    /// All paragraph runs extracted from FKP pages, sorted by start CP
+    runs: Vec<ParagraphRun>,
+}
+
+impl PapBinTable {
+    /// Parse PAPBinTable from PlcfBtePapx data.
+    ///
+    /// # Arguments
+    ///
+    /// * `plcf_bte_papx_data` - The PlcfBtePapx data from table stream
+    /// * `word_document` - The WordDocument stream (FKP pages are stored here)
+    /// * `piece_table` - The piece table for FC-to-CP conversion
+    pub fn parse(
+        plcf_bte_papx_data: &[u8],
+        word_document: &[u8],
+        piece_table: &PieceTable,
+    ) -> Option<Self> {
+        Self::parse_with_pages(
+            plcf_bte_papx_data,
+            |pn| fkp_page(word_document, pn),
+            piece_table,
+        )
+    }
+
+    /// Parse PAPBinTable from PlcfBtePapx data, getting each FKP page by
+    /// its page number from `page`.
+    ///
+    /// Pages that `page` doesn't return are skipped.
+    pub(crate) fn parse_with_pages<'p>(
+        plcf_bte_papx_data: &[u8],
+        page: impl Fn(u32) -> Option<&'p [u8]>,
+        piece_table: &PieceTable,
+    ) -> Option<Self> {
+        if plcf_bte_papx_data.len() < 8 {
+            return None;
+        }
+
+        // Same layout as PlcfBteChpx: (n+1) FCs followed by n PnFkpPapx
+        let mut runs = Vec::new();
+
+        for (_, pn) in fkp_pages(plcf_bte_papx_data) {
+            // The PapxFkp is at pn * 512 in the WordDocument stream
+            let Some(fkp_page) = page(pn) else {
+                continue;
+            };
+
+            let Some(fkp) = PapxFkp::parse(fkp_page, &[]) else {
+                continue;
+            };
+
+            // The FC array has one more entry than there are paragraphs
+            let fc_end = read_u32_le(fkp_page, fkp.count() * 4).unwrap_or(0);
+            for j in 0..fkp.count() {
+                let Some(entry) = fkp.entry(j) else {
+                    continue;
+                };
+                let end_fc = fkp.entry(j + 1).map(|e| e.fc).unwrap_or(fc_end);
+
+                let start_cp = piece_table.fc_to_cp(entry.fc).unwrap_or(entry.fc);
+                let end_cp = piece_table
+                    .fc_to_cp(end_fc)
+                    .or_else(|| {
+                        // The final FC is one past the last byte and may fall outside every piece
+                        piece_table
+                            .fc_to_cp(end_fc.saturating_sub(1))
+                            .map(|cp| cp + 1)
+                    })
+                    .unwrap_or(end_fc);
+                if start_cp >= end_cp {
+                    continue;
+                }
+
+                runs.push(ParagraphRun {
+                    start_cp,
+                    end_cp,
+                    properties: Self::parse_papx(&entry.grpprl),
+                });
+            }
+        }
+
+        runs.sort_unstable_by_key(|r| (r.start_cp, r.end_cp));
+        Some(Self { runs })
+    }
+
+    /// Parse the istd and grpprl of a PAPX.
+    fn parse_papx(grpprl_and_istd: &[u8]) -> ParagraphProperties {
+        let Ok(istd) = read_u16_le(grpprl_and_istd, 0) else {
+            return ParagraphProperties::default();
+        };
+        let mut properties =
+            ParagraphProperties::from_sprm(&grpprl_and_istd[2..]).unwrap_or_default();
+        properties.style_index = Some(istd);
+        properties
+    }
+
+    /// Get all paragraph runs.
+    #[inline]
+    pub fn runs(&self) -> &[ParagraphRun] {
+        &self.runs
+    }
+
+    /// Find the paragraph properties in effect at a character position.
+    ///
+    /// Pass the CP of a paragraph mark to get that paragraph's properties.
+    pub fn properties_at(&self, cp: u32) -> Option<&ParagraphProperties> {
+        let index = self.runs.partition_point(|run| run.start_cp <= cp);
+        let run = self.runs.get(index.checked_sub(1)?)?;
+        (cp < run.end_cp).then_some(&run.properties)
+    }
+}
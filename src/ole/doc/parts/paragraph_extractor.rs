@@ -8,6 +8,8 @@ use super::chp::CharacterProperties;
 use super::chp_bin_table::ChpBinTable;
 use super::fib::FileInformationBlock;
 use super::pap::ParagraphProperties;
+use super::pap_bin_table::PapBinTable;
+use super::text::CP_PADDING;
 use crate::ole::plcf::PlcfParser;
 use crate::ole::sprm::parse_sprms;
 use std::sync::Arc;
@@ -19,6 +21,15 @@ pub(crate) type ExtractedParagraph = (
     Vec<(String, CharacterProperties)>,
 );
 
+/// Check whether a character ends a paragraph.
+///
+/// Paragraphs end with a paragraph mark (CR), or with a cell mark (0x07) for
+/// the last paragraph of a table cell and the row end paragraph.
+#[inline]
+pub(crate) fn is_paragraph_mark(c: char) -> bool {
+    c == '\r' || c == '\u{7}'
+}
+
 /// Paragraph extractor using binary structures.
 ///
 /// Based on Apache POI's ParagraphPropertiesTable (PAPBinTable) and
@@ -28,10 +39,14 @@ pub struct ParagraphExtractor<'a> {
     pap_plcf: Option<PlcfParser>,
     /// Character property bin table (shared reference to avoid re-parsing)
     chp_bin_table: Option<&'a ChpBinTable>,
+    /// Paragraph property bin table (shared reference to avoid re-parsing)
+    pap_bin_table: Option<&'a PapBinTable>,
     /// The extracted text (shared via Arc to avoid cloning, thread-safe)
     text: Arc<String>,
     /// Text piece character positions
     text_ranges: Vec<(u32, u32, usize)>, // (cp_start, cp_end, text_offset)
+    /// Character position of the first character of `text`
+    text_start_cp: u32,
     /// Character position range to extract (for subdocuments)
     cp_range: Option<(u32, u32)>,
 }
@@ -77,8 +92,10 @@ impl<'a> ParagraphExtractor<'a> {
         Ok(Self {
             pap_plcf,
             chp_bin_table,
+            pap_bin_table: None,
             text,
             text_ranges,
+            text_start_cp: 0,
             cp_range: None,
         })
     }
@@ -106,6 +123,23 @@ impl<'a> ParagraphExtractor<'a> {
         Ok(extractor)
     }
 
+    /// Use a pre-parsed paragraph property bin table for paragraph properties.
+    ///
+    /// The properties of each paragraph are then looked up at its paragraph mark.
+    pub fn with_pap_bin_table(mut self, pap_bin_table: Option<&'a PapBinTable>) -> Self {
+        self.pap_bin_table = pap_bin_table;
+        self
+    }
+
+    /// Treat `text` as the part of the document text starting at `text_start_cp`.
+    ///
+    /// Used to extract the paragraphs of the document one stretch of text
+    /// at a time; the CP range must lie within that stretch.
+    pub fn with_text_start_cp(mut self, text_start_cp: u32) -> Self {
+        self.text_start_cp = text_start_cp;
+        self
+    }
+
     /// Build mapping from character positions to text offsets.
     fn build_text_ranges(text: &str) -> Vec<(u32, u32, usize)> {
         let mut ranges = Vec::new();
@@ -126,7 +160,8 @@ impl<'a> ParagraphExtractor<'a> {
     /// Returns a vector of (text, paragraph_properties, character_runs) tuples.
     ///
     /// Based on MS-DOC specification and Apache POI's approach:
-    /// Paragraphs in Word documents are delimited by CR (\r = 0x000D) characters.
+    /// Paragraphs in Word documents are delimited by CR (\r = 0x000D) characters,
+    /// or by cell marks (0x0007) inside tables.
     /// The PAP PLCF stores formatting properties, but doesn't define paragraph boundaries.
     pub fn extract_paragraphs(&self) -> Result<Vec<ExtractedParagraph>> {
         let mut paragraphs = Vec::new();
@@ -136,15 +171,15 @@ impl<'a> ParagraphExtractor<'a> {
         let doc_end_cp = self
             .cp_range
             .map(|(_, end)| end)
-            .unwrap_or(self.text.chars().count() as u32);
+            .unwrap_or(self.text_start_cp + self.text.chars().count() as u32);
 
-        // Find all paragraph breaks (CR characters) in the text
-        // CR (0x000D / '\r') marks the end of each paragraph in Word documents
+        // Find all paragraph breaks (paragraph and cell marks) in the text
         let mut para_boundaries = vec![doc_start_cp];
         let mut current_cp = doc_start_cp;
 
-        for c in self.text.chars().skip(doc_start_cp as usize) {
-            if c == '\r' {
+        let skip = doc_start_cp.saturating_sub(self.text_start_cp) as usize;
+        for c in self.text.chars().skip(skip) {
+            if is_paragraph_mark(c) {
                 para_boundaries.push(current_cp + 1); // Position after CR
             }
             current_cp += 1;
@@ -167,16 +202,24 @@ impl<'a> ParagraphExtractor<'a> {
                 continue;
             }
 
-            // Extract paragraph text (excluding the CR marker itself)
+            // The mark ending the paragraph, if any
+            let mark = self.char_at(para_end - 1).filter(|&c| is_paragraph_mark(c));
+
+            // Extract paragraph text (excluding the mark itself)
             let mut para_text = self.extract_text_range(para_start, para_end);
-            // Remove trailing CR if present
-            if para_text.ends_with('\r') {
+            if mark.is_some() {
                 para_text.pop();
             }
 
             // Find matching PAP properties for this paragraph
-            // PAP PLCF entries define formatting, not boundaries
-            let para_props = if let Some(ref pap_plcf) = self.pap_plcf {
+            let mut para_props = if let Some(pap_bin_table) = self.pap_bin_table {
+                // The PAPX of a paragraph covers its paragraph mark
+                pap_bin_table
+                    .properties_at(para_end - 1)
+                    .cloned()
+                    .unwrap_or_default()
+            } else if let Some(ref pap_plcf) = self.pap_plcf {
+                // PAP PLCF entries define formatting, not boundaries
                 let mut found_props = None;
                 for j in 0..pap_plcf.count() {
                     if let Some((pap_start, pap_end)) = pap_plcf.range(j) {
@@ -193,11 +236,10 @@ impl<'a> ParagraphExtractor<'a> {
             } else {
                 ParagraphProperties::default()
             };
+            para_props.ends_cell = mark == Some('\u{7}');
 
-            // Extract character runs within this paragraph (excluding the CR)
-            let para_text_end = if para_end > para_start
-                && self.text.chars().nth((para_end - 1) as usize) == Some('\r')
-            {
+            // Extract character runs within this paragraph (excluding the mark)
+            let para_text_end = if mark.is_some() {
                 para_end - 1
             } else {
                 para_end
@@ -211,7 +253,7 @@ impl<'a> ParagraphExtractor<'a> {
         if paragraphs.is_empty() && !self.text.is_empty() {
             let runs = self.extract_runs(doc_start_cp, doc_end_cp)?;
             paragraphs.push((
-                self.text.as_ref().clone(),
+                self.text.replace(CP_PADDING, ""),
                 ParagraphProperties::default(),
                 runs,
             ));
@@ -220,12 +262,19 @@ impl<'a> ParagraphExtractor<'a> {
         Ok(paragraphs)
     }
 
+    /// Get the character at a character position.
+    fn char_at(&self, cp: u32) -> Option<char> {
+        let index = cp.checked_sub(self.text_start_cp)?;
+        let &(_, _, offset) = self.text_ranges.get(index as usize)?;
+        self.text[offset..].chars().next()
+    }
+
     /// Extract text for a character position range.
     fn extract_text_range(&self, cp_start: u32, cp_end: u32) -> String {
-        // Clamp CPs to valid range
+        // Clamp CPs to valid range, relative to the start of the text
         let max_cp = self.text_ranges.len() as u32;
-        let cp_start_clamped = cp_start.min(max_cp);
-        let cp_end_clamped = cp_end.min(max_cp);
+        let cp_start_clamped = cp_start.saturating_sub(self.text_start_cp).min(max_cp);
+        let cp_end_clamped = cp_end.saturating_sub(self.text_start_cp).min(max_cp);
 
         if cp_start_clamped >= cp_end_clamped {
             return String::new();
@@ -243,7 +292,7 @@ impl<'a> ParagraphExtractor<'a> {
             };
 
             if start_offset <= end_offset {
-                self.text[start_offset..end_offset].to_string()
+                self.text[start_offset..end_offset].replace(CP_PADDING, "")
             } else {
                 String::new()
             }
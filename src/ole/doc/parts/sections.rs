@@ -0,0 +1,180 @@
+/// Section table (PlcfSed) and section properties (SEP) parser for Word
+/// binary format.
+///
+/// Based on Apache POI's SectionTable and SectionSprmUncompressor. Only the
+/// page setup is kept: page size, orientation and margins.
+///
+/// References:
+/// - org.apache.poi.hwpf.model.SectionTable
+/// - [MS-DOC] 2.8.26 PlcfSed, 2.8.24 Sed, 2.9.264 SEPX
+use super::fib::FileInformationBlock;
+use crate::common::binary::{read_u16_le, read_u32_le};
+use crate::ole::plcf::PlcfParser;
+use crate::ole::sprm::{Sprm, parse_sprms};
+use crate::ole::sprm_operations::*;
+
+/// Size of a section descriptor (SED)
+const SED_SIZE: usize = 12;
+
+/// fcSepx of a section without a SEPX
+const NO_SEPX: u32 = 0xFFFF_FFFF;
+
+/// Page setup of a section, in twips.
+///
+/// Values are `None` when the section's SEPX does not set them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SectionProperties {
+    /// First character position of the section
+    pub start_cp: u32,
+    /// Character position after the section
+    pub end_cp: u32,
+    /// Page width (xaPage)
+    pub page_width: Option<u32>,
+    /// Page height (yaPage)
+    pub page_height: Option<u32>,
+    /// Whether pages are landscape (dmOrientPage)
+    pub landscape: Option<bool>,
+    /// Top margin (dyaTop)
+    pub top_margin: Option<u32>,
+    /// Bottom margin (dyaBottom)
+    pub bottom_margin: Option<u32>,
+    /// Left margin (dxaLeft)
+    pub left_margin: Option<u32>,
+    /// Right margin (dxaRight)
+    pub right_margin: Option<u32>,
+    /// Header distance from the top edge (dyaHdrTop)
+    pub header_distance: Option<u32>,
+    /// Footer distance from the bottom edge (dyaHdrBottom)
+    pub footer_distance: Option<u32>,
+    /// Gutter width (dzaGutter)
+    pub gutter: Option<u32>,
+}
+
+impl SectionProperties {
+    /// Apply the SEP sprms of a SEPX grpprl.
+    fn apply_sprms(&mut self, grpprl: &[u8]) {
+        for sprm in parse_sprms(grpprl) {
+            self.apply_sprm(&sprm);
+        }
+    }
+
+    /// Apply a single SEP sprm.
+    fn apply_sprm(&mut self, sprm: &Sprm) {
+        // Margins may be negative to mark them as exact; the size is the
+        // absolute value
+        let twips = || {
+            sprm.operand_i16()
+                .map(|value| u32::from(value.unsigned_abs()))
+        };
+        let unsigned = || sprm.operand_word().map(u32::from);
+
+        match sprm.opcode {
+            SPRM_S_XA_PAGE => self.page_width = unsigned(),
+            SPRM_S_YA_PAGE => self.page_height = unsigned(),
+            SPRM_S_B_ORIENTATION => self.landscape = sprm.operand_byte().map(|value| value == 2),
+            SPRM_S_DYA_TOP => self.top_margin = twips(),
+            SPRM_S_DYA_BOTTOM => self.bottom_margin = twips(),
+            SPRM_S_DXA_LEFT => self.left_margin = unsigned(),
+            SPRM_S_DXA_RIGHT => self.right_margin = unsigned(),
+            SPRM_S_DYA_HDR_TOP => self.header_distance = unsigned(),
+            SPRM_S_DYA_HDR_BOTTOM => self.footer_distance = unsigned(),
+            SPRM_S_DZA_GUTTER => self.gutter = unsigned(),
+            _ => {},
+        }
+    }
+}
+
+/// The sections of a document, in document order.
+#[derive(Debug, Clone, Default)]
+pub struct SectionTable {
+    sections: Vec<SectionProperties>,
+}
+
+impl SectionTable {
+    /// Parse the section table referenced by the FIB.
+    ///
+    /// # Arguments
+    ///
+    /// * `fib` - File Information Block
+    /// * `table_stream` - Table stream (0Table or 1Table) data
+    /// * `word_document` - WordDocument stream data, which holds the SEPXs
+    pub fn parse(
+        fib: &FileInformationBlock,
+        table_stream: &[u8],
+        word_document: &[u8],
+    ) -> Option<Self> {
+        // Index 6 in FibRgFcLcb97 is fcPlcfSed/lcbPlcfSed
+        let (offset, length) = fib.get_table_pointer(6)?;
+        let start = offset as usize;
+        let end = start.checked_add(length as usize)?.min(table_stream.len());
+        if length == 0 || start >= end {
+            return None;
+        }
+        let plcf = PlcfParser::parse(&table_stream[start..end], SED_SIZE)?;
+
+        let sections = (0..plcf.count())
+            .filter_map(|index| {
+                let (start_cp, end_cp) = plcf.range(index)?;
+                let mut section = SectionProperties {
+                    start_cp,
+                    end_cp,
+                    ..SectionProperties::default()
+                };
+                // fcSepx follows the 2-byte fn field of the SED
+                let fc_sepx = read_u32_le(plcf.property(index)?, 2).ok()?;
+                if fc_sepx != NO_SEPX
+                    && let Some(grpprl) = Self::sepx_grpprl(word_document, fc_sepx as usize)
+                {
+                    section.apply_sprms(grpprl);
+                }
+                Some(section)
+            })
+            .collect();
+
+        Some(Self { sections })
+    }
+
+    /// Get the grpprl of the SEPX at `offset`, which starts with its size.
+    fn sepx_grpprl(word_document: &[u8], offset: usize) -> Option<&[u8]> {
+        let size = read_u16_le(word_document, offset).ok()? as usize;
+        word_document.get(offset + 2..offset + 2 + size)
+    }
+
+    /// Get the sections in document order.
+    #[inline]
+    pub fn sections(&self) -> &[SectionProperties] {
+        &self.sections
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_sepx_sprms() {
+        let mut grpprl = Vec::new();
+        for (opcode, operand) in [
+            (SPRM_S_XA_PAGE, 16838i16),
+            (SPRM_S_YA_PAGE, 11906),
+            (SPRM_S_DYA_TOP, -1440),
+            (SPRM_S_DXA_LEFT, 1134),
+        ] {
+            grpprl.extend_from_slice(&opcode.to_le_bytes());
+            grpprl.extend_from_slice(&operand.to_le_bytes());
+        }
+        grpprl.extend_from_slice(&SPRM_S_B_ORIENTATION.to_le_bytes());
+        grpprl.push(2);
+
+        let mut section = SectionProperties::default();
+        section.apply_sprms(&grpprl);
+        assert_eq!(section.page_width, Some(16838));
+        assert_eq!(section.page_height, Some(11906));
+        assert_eq!(section.landscape, Some(true));
+        assert_eq!(section.top_margin, Some(1440));
+        assert_eq!(section.left_margin, Some(1134));
+        // Properties the SEPX does not set stay unknown
+        assert_eq!(section.bottom_margin, None);
+        assert_eq!(section.gutter, None);
+    }
+}
@@ -0,0 +1,363 @@
+/// Style sheet (STSH) parser for Word binary format.
+///
+/// Based on Apache POI's StyleSheet and StyleDescription classes.
+/// Only the data needed to resolve outline levels, lists and languages is
+/// kept.
+///
+/// References:
+/// - org.apache.poi.hwpf.model.StyleSheet
+/// - [MS-DOC] 2.9.271 STSH, 2.9.260 STD
+use super::chp::CharacterProperties;
+use super::fib::FileInformationBlock;
+use super::pap::ParagraphProperties;
+use crate::common::Language;
+use crate::common::binary::read_u16_le;
+
+/// Style kind (stk) of paragraph styles.
+const STK_PARAGRAPH: u16 = 1;
+
+/// Style kind (stk) of character styles.
+const STK_CHARACTER: u16 = 2;
+
+/// Istd value marking "no base style".
+const ISTD_NIL: u16 = 0x0FFF;
+
+/// Outline level meaning "body text" rather than a heading.
+const OUTLINE_BODY_TEXT: u8 = 9;
+
+/// A single style definition (STD).
+#[derive(Debug, Clone)]
+pub struct StyleDefinition {
+    /// Style name
+    pub name: String,
+    /// Built-in style identifier (sti); 1-9 are "heading 1" to "heading 9"
+    pub sti: u16,
+    /// Index of the style this one is based on
+    pub base_style: Option<u16>,
+    /// Paragraph properties defined by the style (paragraph styles only)
+    pub paragraph_properties: Option<ParagraphProperties>,
+    /// Character properties defined by the style (paragraph and character
+    /// styles)
+    pub character_properties: Option<CharacterProperties>,
+}
+
+/// The document style sheet, indexed by istd.
+#[derive(Debug, Clone, Default)]
+pub struct StyleSheet {
+    styles: Vec<Option<StyleDefinition>>,
+}
+
+impl StyleSheet {
+    /// Parse the style sheet referenced by the FIB.
+    ///
+    /// # Arguments
+    ///
+    /// * `fib` - File Information Block
+    /// * `table_stream` - Table stream (0Table or 1Table) data
+    pub fn parse(fib: &FileInformationBlock, table_stream: &[u8]) -> Option<Self> {
+        // Index 1 in FibRgFcLcb97 is fcStshf/lcbStshf
+        let (offset, length) = fib.get_table_pointer(1)?;
+        let start = offset as usize;
+        let end = start.checked_add(length as usize)?.min(table_stream.len());
+        if length == 0 || start >= end {
+            return None;
+        }
+        Self::from_bytes(&table_stream[start..end])
+    }
+
+    /// Parse a style sheet from raw STSH bytes.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        // LPStshi: cbStshi followed by the STSHI
+        let cb_stshi = read_u16_le(data, 0).ok()? as usize;
+        let cstd = read_u16_le(data, 2).ok()? as usize;
+        let cb_std_base = read_u16_le(data, 4).ok()? as usize;
+
+        let mut styles = Vec::with_capacity(cstd);
+        let mut offset = 2 + cb_stshi;
+        for _ in 0..cstd {
+            let Ok(cb_std) = read_u16_le(data, offset) else {
+                break;
+            };
+            offset += 2;
+            let cb_std = cb_std as usize;
+            if cb_std == 0 {
+                // Empty slot
+                styles.push(None);
+                continue;
+            }
+            let Some(std) = data.get(offset..offset + cb_std) else {
+                break;
+            };
+            styles.push(Self::parse_std(std, cb_std_base));
+            offset += cb_std;
+        }
+
+        Some(Self { styles })
+    }
+
+    /// Parse one STD structure.
+    fn parse_std(std: &[u8], cb_std_base: usize) -> Option<StyleDefinition> {
+        // StdfBase: sti (12 bits) + flags, stk (4 bits) + istdBase (12 bits),
+        // cupx (4 bits) + istdNext (12 bits), bchUpe, grfstd
+        let sti = read_u16_le(std, 0).ok()? & 0x0FFF;
+        let stk_and_base = read_u16_le(std, 2).ok()?;
+        let stk = stk_and_base & 0x000F;
+        let istd_base = stk_and_base >> 4;
+        let cupx = read_u16_le(std, 4).ok()? & 0x000F;
+
+        // Xstz name: character count, UTF-16 characters and a null terminator
+        let mut offset = cb_std_base;
+        let cch = read_u16_le(std, offset).ok()? as usize;
+        offset += 2;
+        let name_bytes = std.get(offset..offset + cch * 2)?;
+        let name = String::from_utf16_lossy(
+            &name_bytes
+                .chunks_exact(2)
+                .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+                .collect::<Vec<_>>(),
+        );
+        offset += cch * 2 + 2;
+
+        // UPXs, each padded to an even length
+        let mut upxs = Vec::with_capacity(cupx as usize);
+        for _ in 0..cupx {
+            offset += offset % 2;
+            let Ok(cb_upx) = read_u16_le(std, offset) else {
+                break;
+            };
+            offset += 2;
+            let Some(upx) = std.get(offset..offset + cb_upx as usize) else {
+                break;
+            };
+            upxs.push(upx);
+            offset += cb_upx as usize;
+        }
+
+        // A paragraph style has a PAPX (istd followed by a grpprl) and then a
+        // CHPX; a character style has only a CHPX
+        let (papx, chpx) = match stk {
+            STK_PARAGRAPH => (upxs.first(), upxs.get(1)),
+            STK_CHARACTER => (None, upxs.first()),
+            _ => (None, None),
+        };
+        let paragraph_properties = papx
+            .filter(|upx| upx.len() >= 2)
+            .and_then(|upx| ParagraphProperties::from_sprm(&upx[2..]).ok());
+        let character_properties = chpx.and_then(|upx| CharacterProperties::from_sprm(upx).ok());
+
+        Some(StyleDefinition {
+            name,
+            sti,
+            base_style: (istd_base != ISTD_NIL).then_some(istd_base),
+            paragraph_properties,
+            character_properties,
+        })
+    }
+
+    /// Get a style by its index (istd).
+    #[inline]
+    pub fn style(&self, istd: u16) -> Option<&StyleDefinition> {
+        self.styles.get(istd as usize)?.as_ref()
+    }
+
+    /// Get the number of style slots.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.styles.len()
+    }
+
+    /// Check whether the style sheet has no styles.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.styles.is_empty()
+    }
+
+    /// Resolve the outline level (0-8) of a paragraph style.
+    ///
+    /// Uses the style's own outline level when set, then the built-in heading
+    /// styles, then the style it is based on. Returns `None` for body text.
+    pub fn outline_level(&self, istd: u16) -> Option<u8> {
+        let mut current = Some(istd);
+        // Guard against cycles in malformed base style chains
+        for _ in 0..self.styles.len().min(64) {
+            let style = self.style(current?)?;
+            if let Some(level) = style
+                .paragraph_properties
+                .as_ref()
+                .and_then(|pap| pap.outline_level)
+            {
+                return (level < OUTLINE_BODY_TEXT).then_some(level);
+            }
+            if (1..=9).contains(&style.sti) {
+                return Some(style.sti as u8 - 1);
+            }
+            current = style.base_style;
+        }
+        None
+    }
+
+    /// Resolve the list (ilfo) and list level (ilvl) of a paragraph style.
+    ///
+    /// Follows the base style chain until a style sets a list. The level
+    /// defaults to 0. Returns `None` for styles without numbering.
+    pub fn list_format(&self, istd: u16) -> Option<(i16, u8)> {
+        let mut current = Some(istd);
+        let mut level = None;
+        // Guard against cycles in malformed base style chains
+        for _ in 0..self.styles.len().min(64) {
+            let style = self.style(current?)?;
+            if let Some(pap) = style.paragraph_properties.as_ref() {
+                level = level.or(pap.list_level);
+                if let Some(ilfo) = pap.list_format_override {
+                    return (ilfo > 0).then_some((ilfo, level.unwrap_or(0)));
+                }
+            }
+            current = style.base_style;
+        }
+        None
+    }
+
+    /// Resolve the languages of a style through its base style chain.
+    ///
+    /// Paragraph styles are normally based on "Normal" (istd 0), whose
+    /// languages act as the document defaults.
+    pub fn language(&self, istd: u16) -> Language {
+        let mut chain = Vec::new();
+        let mut current = Some(istd);
+        // Guard against cycles in malformed base style chains
+        while chain.len() < self.styles.len().min(64)
+            && let Some(style) = current.and_then(|istd| self.style(istd))
+        {
+            chain.push(style);
+            current = style.base_style;
+        }
+
+        let mut language = Language::default();
+        for style in chain.iter().rev() {
+            if let Some(chp) = style.character_properties.as_ref() {
+                language.apply(&chp.language());
+            }
+        }
+        language
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build an STD for a paragraph style with an optional grpprl.
+    fn std_bytes(sti: u16, istd_base: u16, name: &str, grpprl: &[u8]) -> Vec<u8> {
+        let mut std = Vec::new();
+        std.extend_from_slice(&sti.to_le_bytes());
+        std.extend_from_slice(&((istd_base << 4) | STK_PARAGRAPH).to_le_bytes());
+        std.extend_from_slice(&2u16.to_le_bytes()); // cupx
+        std.extend_from_slice(&0u16.to_le_bytes()); // bchUpe
+        std.extend_from_slice(&0u16.to_le_bytes()); // grfstd
+        let name: Vec<u16> = name.encode_utf16().collect();
+        std.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        for ch in name {
+            std.extend_from_slice(&ch.to_le_bytes());
+        }
+        std.extend_from_slice(&0u16.to_le_bytes());
+        std.extend_from_slice(&((grpprl.len() + 2) as u16).to_le_bytes());
+        std.extend_from_slice(&0u16.to_le_bytes()); // istd
+        std.extend_from_slice(grpprl);
+        std
+    }
+
+    /// Append a CHPX UPX to an STD built by [`std_bytes`].
+    fn with_chpx(mut std: Vec<u8>, grpprl: &[u8]) -> Vec<u8> {
+        if std.len() % 2 == 1 {
+            std.push(0);
+        }
+        std.extend_from_slice(&(grpprl.len() as u16).to_le_bytes());
+        std.extend_from_slice(grpprl);
+        std
+    }
+
+    /// Build an STD for a character style with a CHPX.
+    fn character_std_bytes(istd_base: u16, name: &str, grpprl: &[u8]) -> Vec<u8> {
+        let mut std = std_bytes(0x0FFE, istd_base, name, &[]);
+        std[2] = (std[2] & 0xF0) | STK_CHARACTER as u8;
+        std[4] = 1; // cupx
+        // Replace the PAPX with the CHPX
+        std.truncate(std.len() - 4);
+        with_chpx(std, grpprl)
+    }
+
+    fn stsh_bytes(stds: &[Vec<u8>]) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&4u16.to_le_bytes()); // cbStshi
+        data.extend_from_slice(&(stds.len() as u16 + 1).to_le_bytes()); // cstd
+        data.extend_from_slice(&10u16.to_le_bytes()); // cbSTDBaseInFile
+        for std in stds {
+            data.extend_from_slice(&(std.len() as u16).to_le_bytes());
+            data.extend_from_slice(std);
+        }
+        // Trailing empty slot
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_outline_levels() {
+        let data = stsh_bytes(&[
+            std_bytes(0, ISTD_NIL, "Normal", &[]),
+            std_bytes(1, 0, "heading 1", &[]),
+            // Custom style with sprmPOutLvl = 2
+            std_bytes(0x0FFE, 0, "Chapter", &[0x40, 0x26, 0x02]),
+            // Based on "Chapter" without its own level
+            std_bytes(0x0FFE, 2, "Chapter Title", &[]),
+            // Explicitly body text
+            std_bytes(0x0FFE, 2, "Not A Heading", &[0x40, 0x26, 0x09]),
+        ]);
+        let stylesheet = StyleSheet::from_bytes(&data).unwrap();
+
+        assert_eq!(stylesheet.len(), 6);
+        assert_eq!(stylesheet.style(1).unwrap().name, "heading 1");
+        assert!(stylesheet.style(5).is_none());
+        assert_eq!(stylesheet.outline_level(0), None);
+        assert_eq!(stylesheet.outline_level(1), Some(0));
+        assert_eq!(stylesheet.outline_level(2), Some(2));
+        assert_eq!(stylesheet.outline_level(3), Some(2));
+        assert_eq!(stylesheet.outline_level(4), None);
+        assert_eq!(stylesheet.outline_level(42), None);
+    }
+
+    #[test]
+    fn test_style_languages() {
+        let data = stsh_bytes(&[
+            // Normal: en-US, ja-JP, ar-SA
+            with_chpx(
+                std_bytes(0, ISTD_NIL, "Normal", &[]),
+                &[
+                    0x73, 0x48, 0x09, 0x04, 0x74, 0x48, 0x11, 0x04, 0x5F, 0x48, 0x01, 0x04,
+                ],
+            ),
+            // Based on Normal with German Latin text
+            with_chpx(
+                std_bytes(0x0FFE, 0, "German", &[]),
+                &[0x6D, 0x48, 0x07, 0x04],
+            ),
+            // Character style setting Korean East Asian text
+            character_std_bytes(ISTD_NIL, "Korean", &[0x74, 0x48, 0x12, 0x04]),
+        ]);
+        let stylesheet = StyleSheet::from_bytes(&data).unwrap();
+
+        let normal = stylesheet.language(0);
+        assert_eq!(normal.latin.as_deref(), Some("en-US"));
+        assert_eq!(normal.east_asian.as_deref(), Some("ja-JP"));
+        assert_eq!(normal.complex.as_deref(), Some("ar-SA"));
+
+        let german = stylesheet.language(1);
+        assert_eq!(german.latin.as_deref(), Some("de-DE"));
+        assert_eq!(german.east_asian.as_deref(), Some("ja-JP"));
+        assert!(stylesheet.style(1).unwrap().paragraph_properties.is_some());
+
+        let korean = stylesheet.language(2);
+        assert_eq!(korean.latin, None);
+        assert_eq!(korean.east_asian.as_deref(), Some("ko-KR"));
+        assert!(stylesheet.language(42).is_empty());
+    }
+}
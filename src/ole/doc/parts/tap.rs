@@ -48,8 +48,10 @@ pub struct TableProperties {
 /// Based on Apache POI's TableCellDescriptor class.
 #[derive(Debug, Clone, Default)]
 pub struct CellProperties {
-    /// Merged cell status
+    /// Horizontal merge status
     pub merge_status: CellMergeStatus,
+    /// Vertical merge status (`Merged` continues the cell above)
+    pub vertical_merge: CellMergeStatus,
     /// Vertical alignment
     pub vertical_alignment: VerticalAlignment,
     /// Cell background color (RGB)
@@ -29,8 +29,9 @@ use super::tap::{
     TableWidth, TextDirection, VerticalAlignment, WidthType,
 };
 use crate::common::binary::{BinaryResult, read_i16_le, read_u16_le};
+use crate::common::trace;
 use crate::ole::sprm::{Sprm, parse_sprms};
-use crate::ole::sprm_operations::get_sprm_operation;
+use crate::ole::sprm_operations::{get_sprm_operation, get_sprm_type};
 use bumpalo::Bump;
 
 /// TAP parser with arena allocation for temporary structures.
@@ -88,29 +89,24 @@ impl<'arena> TapParser<'arena> {
     /// and cell boundaries.
     fn find_and_init_tap(&self, sprms: &[Sprm]) -> Result<TableProperties> {
         for sprm in sprms {
-            if sprm.opcode == 0xD608 {
-                // Found sprmTDefTable
-                // For long SPRMs (0xD608), operand format is:
-                // - 2 bytes: size (already parsed, included in operand)
-                // - 1 byte: itcMac (cell count)
-                // - rest: cell boundaries and descriptors
-                if sprm.operand.len() >= 3 {
-                    let cell_count = sprm.operand[2] as usize; // Skip 2-byte size field
-                    return Ok(TableProperties::with_cell_count(cell_count));
-                }
+            // sprmTDefTable: the operand starts with itcMac (cell count)
+            if sprm.opcode == 0xD608
+                && let Some(cell_count) = sprm.operand_byte()
+            {
+                return Ok(TableProperties::with_cell_count(cell_count as usize));
             }
         }
 
         // No table definition found - use default with 1 cell
-        eprintln!("WARNING: Table row didn't specify number of columns in SPRMs");
+        trace::warn!("table row has no column count in its SPRMs, assuming one cell");
         Ok(TableProperties::with_cell_count(1))
     }
 
     /// Check if a SPRM is a TAP (table) SPRM.
     ///
-    /// TAP SPRMs have type 5 (bits 0-2 of opcode).
+    /// TAP SPRMs have sgc 5 (bits 10-12 of the opcode).
     fn is_tap_sprm(opcode: u16) -> bool {
-        (opcode & 0x07) == 5
+        get_sprm_type(opcode) == 5
     }
 
     /// Apply a single SPRM to table properties.
@@ -120,10 +116,11 @@ impl<'arena> TapParser<'arena> {
         &self,
         tap: &mut TableProperties,
         sprm: &Sprm,
-        grpprl: &[u8],
+        _grpprl: &[u8],
     ) -> Result<()> {
         // Use shared SPRM operation extraction
         let operation = get_sprm_operation(sprm.opcode);
+        let operand = sprm.operand_bytes();
 
         match operation {
             // sprmTJc (0x5400) - Table justification
@@ -169,9 +166,9 @@ impl<'arena> TapParser<'arena> {
                     tap.is_header_row = flag != 0;
                 }
             },
-            // sprmTTableBorders (0xD605) - Table borders
+            // sprmTTableBorders80 (0xD605) - Table borders
             0x05 => {
-                self.parse_table_borders(tap, sprm, grpprl)?;
+                self.parse_table_borders(tap, operand)?;
             },
             // 0x06 - obsolete (Word 1.x)
             0x06 => {},
@@ -183,35 +180,78 @@ impl<'arena> TapParser<'arena> {
             },
             // sprmTDefTable (0xD608) - Table definition
             0x08 => {
-                self.parse_table_definition(tap, sprm, grpprl)?;
+                self.parse_table_definition(tap, operand)?;
             },
-            // sprmTDefTableShd (0xD609 / 0xD60A) - Table shading
-            0x09 | 0x0A => {
-                // Parse cell shading information
-                // Format: variable length array of ShadingDescriptor80 (2 bytes each)
-                // Based on Apache POI's handling of sprmTDefTableShd
-                self.parse_cell_shading(tap, sprm, grpprl)?;
+            // sprmTDefTableShd80 (0xD609) - Cell shading, Word 97 colors
+            0x09 => {
+                self.parse_cell_shading_80(tap, operand);
             },
-            // sprmTTlp (0x740B) - Table look specifier
-            0x0B => {
+            // sprmTTlp (0x740A) - Table look specifier
+            0x0A => {
                 // Table look specifier for table styles
-                // This is a complex structure that defines table style properties
-                // For basic parsing, we can skip this as it's mainly for styling
-                if let Some(tlp) = sprm.operand_dword() {
-                    // TLP structure contains bit flags for various table style options
-                    // Not critical for basic text extraction
-                    let _ = tlp;
-                }
+                // This is mainly for styling and not needed for the table layout
+            },
+            // sprmTDefTableShd3rd (0xD60C) - Shading of cells 44 to 62
+            0x0C => {
+                self.parse_cell_shading(tap, operand, 44);
+            },
+            // sprmTDefTableShd (0xD612) - Shading of cells 0 to 21
+            0x12 => {
+                self.parse_cell_shading(tap, operand, 0);
+            },
+            // sprmTDefTableShd2nd (0xD616) - Shading of cells 22 to 43
+            0x16 => {
+                self.parse_cell_shading(tap, operand, 22);
             },
             // sprmTInsert (0x7621) - Insert cells
             0x21 => {
-                self.handle_insert_cells(tap, sprm)?;
+                self.handle_insert_cells(tap, operand);
+            },
+            // sprmTMerge (0x5624) - Merge cells horizontally
+            0x24 => {
+                if let [first, lim, ..] = *operand {
+                    for (i, cell) in Self::cells_mut(tap, first, lim).enumerate() {
+                        cell.merge_status = if i == 0 {
+                            CellMergeStatus::First
+                        } else {
+                            CellMergeStatus::Merged
+                        };
+                    }
+                }
+            },
+            // sprmTSplit (0x5625) - Undo a horizontal merge
+            0x25 => {
+                if let [first, lim, ..] = *operand {
+                    for cell in Self::cells_mut(tap, first, lim) {
+                        cell.merge_status = CellMergeStatus::None;
+                    }
+                }
+            },
+            // sprmTVertMerge (0xD62B) - Vertical merge of one cell
+            0x2B => {
+                if let [itc, flag, ..] = *operand
+                    && let Some(cell) = tap.cell_properties.get_mut(itc as usize)
+                {
+                    cell.vertical_merge = Self::vertical_merge(flag);
+                }
+            },
+            // sprmTVertAlign (0xD62C) - Vertical alignment of a range of cells
+            0x2C => {
+                if let [first, lim, align, ..] = *operand {
+                    for cell in Self::cells_mut(tap, first, lim) {
+                        cell.vertical_alignment = Self::vertical_alignment(align);
+                    }
+                }
+            },
+            // sprmTSetBrc (0xD62F) - Borders of a range of cells
+            0x2F => {
+                self.parse_cell_borders(tap, operand);
             },
             // sprmTCellPaddingDefault (0xD634) - Default cell padding
             0x34 => {
-                self.parse_cell_padding(tap, sprm, grpprl)?;
+                self.parse_cell_padding(tap, operand)?;
             },
-            // Other table SPRMs (0x22-0x2C, etc.)
+            // Other table SPRMs
             _ => {
                 // Unknown or unhandled SPRM - skip
             },
@@ -220,66 +260,80 @@ impl<'arena> TapParser<'arena> {
         Ok(())
     }
 
+    /// Get the cells `first..lim` of a row, stopping at the last cell.
+    fn cells_mut(
+        tap: &mut TableProperties,
+        first: u8,
+        lim: u8,
+    ) -> impl Iterator<Item = &mut CellProperties> {
+        let lim = (lim as usize).min(tap.cell_properties.len());
+        let first = (first as usize).min(lim);
+        tap.cell_properties[first..lim].iter_mut()
+    }
+
+    /// Decode a VerticalMergeFlag.
+    fn vertical_merge(flag: u8) -> CellMergeStatus {
+        match flag & 0x03 {
+            // fvmRestart: first cell of the merged set
+            3 => CellMergeStatus::First,
+            // fvmMerge: merged with the cell above
+            1 => CellMergeStatus::Merged,
+            _ => CellMergeStatus::None,
+        }
+    }
+
+    /// Decode a VerticalAlign value.
+    fn vertical_alignment(align: u8) -> VerticalAlignment {
+        match align {
+            1 => VerticalAlignment::Center,
+            2 => VerticalAlignment::Bottom,
+            _ => VerticalAlignment::Top,
+        }
+    }
+
     /// Parse table definition (sprmTDefTable - 0xD608).
     ///
     /// Format:
     /// - 1 byte: itcMac (cell count)
     /// - (itcMac+1) * 2 bytes: rgdxaCenter (cell boundaries)
     /// - itcMac * 20 bytes: rgtc (cell descriptors) [optional]
-    fn parse_table_definition(
-        &self,
-        tap: &mut TableProperties,
-        sprm: &Sprm,
-        grpprl: &[u8],
-    ) -> Result<()> {
-        let offset = sprm.offset + 3; // Skip sprm (2) + size (1)
-        if offset >= grpprl.len() {
+    fn parse_table_definition(&self, tap: &mut TableProperties, data: &[u8]) -> Result<()> {
+        let Some(&itc_mac) = data.first() else {
             return Ok(());
-        }
-
-        // Read cell count
-        let itc_mac = binary_to_doc_result(read_byte(grpprl, offset))? as usize;
+        };
+        let itc_mac = itc_mac as usize;
         tap.cell_count = itc_mac;
 
         // Read cell boundaries (rgdxaCenter)
         let mut boundaries = Vec::with_capacity(itc_mac + 1);
         for i in 0..=itc_mac {
-            let boundary_offset = offset + 1 + (i * 2);
-            if boundary_offset + 1 < grpprl.len() {
-                boundaries.push(binary_to_doc_result(read_i16_le(grpprl, boundary_offset))?);
+            if let Ok(boundary) = read_i16_le(data, 1 + i * 2) {
+                boundaries.push(boundary);
             }
         }
         tap.cell_boundaries = boundaries;
 
-        // Calculate where cell descriptors start
-        let end_of_sprm = offset + sprm.size.saturating_sub(3); // -3 for sprm header
-        let start_of_tcs = offset + 1 + ((itc_mac + 1) * 2);
-        let has_tcs = start_of_tcs < end_of_sprm;
-
-        // Read cell descriptors (TableCellDescriptor - TC)
-        if has_tcs {
-            let mut cell_props = Vec::with_capacity(itc_mac);
-            for i in 0..itc_mac {
-                let tc_offset = start_of_tcs + (i * 20); // Each TC is 20 bytes
-                if tc_offset + 20 <= grpprl.len() {
-                    cell_props.push(self.parse_table_cell_descriptor(grpprl, tc_offset)?);
-                } else {
-                    cell_props.push(CellProperties::default());
-                }
-            }
-            tap.cell_properties = cell_props;
-        } else {
-            // No TC data - use defaults
-            tap.cell_properties = vec![CellProperties::default(); itc_mac];
-        }
+        // Cell descriptors (TC80) follow; cells without one use the defaults
+        let start_of_tcs = 1 + (itc_mac + 1) * 2;
+        tap.cell_properties = (0..itc_mac)
+            .map(|i| {
+                let tc_offset = start_of_tcs + i * 20; // Each TC80 is 20 bytes
+                self.parse_table_cell_descriptor(data, tc_offset)
+            })
+            .collect::<Result<_>>()?;
 
         Ok(())
     }
 
-    /// Parse a TableCellDescriptor (TC) structure.
+    /// Parse a TableCellDescriptor (TC80) structure.
     ///
-    /// TC structure (20 bytes total):
-    /// - bytes 0-1: flags (fVertical, fBackward, fRotateFont, fVertMerge, fVertRestart, etc.)
+    /// TC80 structure (20 bytes total):
+    /// - bytes 0-1: tcgrf flags
+    ///   - bits 0-1: horzMerge (1 = first merged cell, 2 or 3 = merged)
+    ///   - bits 2-4: textFlow
+    ///   - bits 5-6: vertMerge (1 = merged with the cell above, 3 = restart)
+    ///   - bits 7-8: vertAlign
+    ///   - bits 9-11: ftsWidth
     /// - bytes 2-3: wWidth (preferred cell width)
     /// - bytes 4-7: brcTop (top border, 4 bytes)
     /// - bytes 8-11: brcLeft (left border, 4 bytes)
@@ -295,37 +349,31 @@ impl<'arena> TapParser<'arena> {
         // Read flags (bytes 0-1)
         let flags = binary_to_doc_result(read_u16_le(data, offset))?;
 
-        // Bit 0: fVertical - vertical text
-        if (flags & 0x0001) != 0 {
-            props.text_direction = TextDirection::TbRl;
-        }
-
-        // Bits 1-2: vertAlign - vertical alignment
-        let vert_align = (flags >> 4) & 0x03;
-        props.vertical_alignment = match vert_align {
-            0 => VerticalAlignment::Top,
-            1 => VerticalAlignment::Center,
-            2 => VerticalAlignment::Bottom,
-            _ => VerticalAlignment::Top,
-        };
-
-        // Bits 3-4: fVertMerge/fVertRestart - cell merging
-        let merge_flags = (flags >> 3) & 0x03;
-        props.merge_status = match merge_flags {
+        props.merge_status = match flags & 0x03 {
             0 => CellMergeStatus::None,
             1 => CellMergeStatus::First,
-            3 => CellMergeStatus::Merged,
-            _ => CellMergeStatus::None,
+            _ => CellMergeStatus::Merged,
+        };
+
+        props.text_direction = match (flags >> 2) & 0x07 {
+            1 => TextDirection::TbRl,
+            2 => TextDirection::BtLr,
+            3 => TextDirection::LrBt,
+            4 => TextDirection::TbLr,
+            _ => TextDirection::LrTb,
         };
 
+        props.vertical_merge = Self::vertical_merge((flags >> 5) as u8);
+        props.vertical_alignment = Self::vertical_alignment(((flags >> 7) & 0x03) as u8);
+
         // Read preferred width (bytes 2-3)
         let w_width = binary_to_doc_result(read_u16_le(data, offset + 2))? as i16;
-        let fts_width = (flags >> 6) & 0x07; // Width type from flags
+        let fts_width = (flags >> 9) & 0x07; // Width type from flags
         props.preferred_width = Some(TableWidth {
             value: w_width,
             width_type: match fts_width {
-                0 => WidthType::Auto,
-                1 => WidthType::Twips,
+                0 | 1 => WidthType::Auto,
+                3 => WidthType::Twips,
                 2 => WidthType::Percentage,
                 _ => WidthType::Auto,
             },
@@ -340,12 +388,13 @@ impl<'arena> TapParser<'arena> {
         Ok(props)
     }
 
-    /// Parse a BorderCode structure (4 bytes).
+    /// Parse a Brc80 structure (4 bytes).
     ///
-    /// BorderCode format:
+    /// Brc80 format:
     /// - byte 0: dptLineWidth (line width in 1/8 points)
     /// - byte 1: brcType (border type)
-    /// - byte 2-3: ico (color index) or RGB color
+    /// - byte 2: ico (color index)
+    /// - byte 3: dptSpace and flags
     fn parse_border_code(data: &[u8], offset: usize) -> Result<Option<BorderStyle>> {
         if offset + 4 > data.len() {
             return Ok(None);
@@ -353,121 +402,131 @@ impl<'arena> TapParser<'arena> {
 
         let width = binary_to_doc_result(read_byte(data, offset))?;
         let border_type = binary_to_doc_result(read_byte(data, offset + 1))?;
-        let color_word = binary_to_doc_result(read_u16_le(data, offset + 2))?;
+        let ico = binary_to_doc_result(read_byte(data, offset + 2))?;
 
-        // If width is 0 and type is 0, no border
-        if width == 0 && border_type == 0 {
+        // 0xFFFFFFFF means "no border" (brcNil)
+        if (width == 0 && border_type == 0) || (width == 0xFF && border_type == 0xFF) {
             return Ok(None);
         }
 
-        let btype = match border_type {
+        // ico 0 means automatic color
+        let color = (ico != 0).then(|| Self::ico_to_rgb(ico));
+        Ok(Some(BorderStyle {
+            width,
+            color,
+            border_type: Self::border_type(border_type),
+        }))
+    }
+
+    /// Decode a BrcType value.
+    fn border_type(brc_type: u8) -> BorderType {
+        match brc_type {
             0 => BorderType::None,
             1 => BorderType::Single,
             2 => BorderType::Thick,
             3 => BorderType::Double,
-            5 => BorderType::Dotted,
-            6 => BorderType::Dashed,
-            7 => BorderType::DotDash,
-            8 => BorderType::DotDotDash,
-            9 => BorderType::Triple,
-            10 => BorderType::ThinThickSmall,
-            11 => BorderType::ThickThinSmall,
-            12 => BorderType::ThinThickThinSmall,
+            6 => BorderType::Dotted,
+            7 => BorderType::Dashed,
+            8 => BorderType::DotDash,
+            9 => BorderType::DotDotDash,
+            10 => BorderType::Triple,
+            11 => BorderType::ThinThickSmall,
+            12 => BorderType::ThickThinSmall,
+            13 => BorderType::ThinThickThinSmall,
             _ => BorderType::Single,
-        };
-
-        // Color extraction from Word color format (based on POI)
-        let color = if color_word == 0 || color_word == 0xFFFF {
-            None
-        } else {
-            Some((
-                (color_word & 0x1F) as u8 * 8,         // Red (5 bits)
-                ((color_word >> 5) & 0x1F) as u8 * 8,  // Green (5 bits)
-                ((color_word >> 10) & 0x1F) as u8 * 8, // Blue (5 bits)
-            ))
-        };
-
-        Ok(Some(BorderStyle {
-            width,
-            color,
-            border_type: btype,
-        }))
+        }
     }
 
-    /// Parse table borders (sprmTTableBorders - 0xD605).
+    /// Parse table borders (sprmTTableBorders80 - 0xD605).
     ///
-    /// Contains 6 BorderCode structures (4 bytes each):
+    /// Contains 6 Brc80 structures (4 bytes each):
     /// - Top, Left, Bottom, Right, Horizontal, Vertical
-    fn parse_table_borders(
-        &self,
-        tap: &mut TableProperties,
-        sprm: &Sprm,
-        grpprl: &[u8],
-    ) -> Result<()> {
-        let offset = sprm.offset + 3; // Skip SPRM header
-        if offset + 24 > grpprl.len() {
+    fn parse_table_borders(&self, tap: &mut TableProperties, data: &[u8]) -> Result<()> {
+        if data.len() < 24 {
             return Ok(());
         }
 
         // Parse 6 border codes (each 4 bytes)
-        tap.border_top = Self::parse_border_code(grpprl, offset)?;
-        tap.border_left = Self::parse_border_code(grpprl, offset + 4)?;
-        tap.border_bottom = Self::parse_border_code(grpprl, offset + 8)?;
-        tap.border_right = Self::parse_border_code(grpprl, offset + 12)?;
-        tap.border_horizontal = Self::parse_border_code(grpprl, offset + 16)?;
-        tap.border_vertical = Self::parse_border_code(grpprl, offset + 20)?;
+        tap.border_top = Self::parse_border_code(data, 0)?;
+        tap.border_left = Self::parse_border_code(data, 4)?;
+        tap.border_bottom = Self::parse_border_code(data, 8)?;
+        tap.border_right = Self::parse_border_code(data, 12)?;
+        tap.border_horizontal = Self::parse_border_code(data, 16)?;
+        tap.border_vertical = Self::parse_border_code(data, 20)?;
 
         Ok(())
     }
 
+    /// Parse the borders of a range of cells (sprmTSetBrc - 0xD62F).
+    ///
+    /// Format:
+    /// - byte 0: itcFirst (first cell index)
+    /// - byte 1: itcLim (limit cell index, exclusive)
+    /// - byte 2: bordersToApply (top, left, bottom, right bits)
+    /// - bytes 3-10: Brc (COLORREF, dptLineWidth, brcType, dptSpace and flags)
+    fn parse_cell_borders(&self, tap: &mut TableProperties, data: &[u8]) {
+        let [first, lim, edges, r, g, b, auto, width, brc_type, ..] = *data else {
+            return;
+        };
+
+        let border = (brc_type != 0 && brc_type != 0xFF).then(|| BorderStyle {
+            width,
+            color: (auto != 0xFF).then_some((r, g, b)),
+            border_type: Self::border_type(brc_type),
+        });
+        for cell in Self::cells_mut(tap, first, lim) {
+            let borders = &mut cell.borders;
+            for (bit, edge) in [
+                (0x01, &mut borders.top),
+                (0x02, &mut borders.left),
+                (0x04, &mut borders.bottom),
+                (0x08, &mut borders.right),
+            ] {
+                if edges & bit != 0 {
+                    *edge = border;
+                }
+            }
+        }
+    }
+
     /// Handle cell insertion (sprmTInsert - 0x7621).
     ///
     /// Operand format (4 bytes):
     /// - byte 0: index (where to insert)
     /// - byte 1: count (how many cells to insert)
     /// - bytes 2-3: width (width of new cells in twips)
-    fn handle_insert_cells(&self, tap: &mut TableProperties, sprm: &Sprm) -> Result<()> {
-        if let Some(operand) = sprm.operand_dword() {
-            let index = ((operand >> 24) & 0xFF) as usize;
-            let count = ((operand >> 16) & 0xFF) as usize;
-            let width = (operand & 0xFFFF) as i16;
-
-            let itc_mac = tap.cell_count;
-            let insert_at = index.min(itc_mac);
-
-            // Create new arrays with space for inserted cells
-            let mut new_boundaries = Vec::with_capacity(itc_mac + count + 1);
-            let mut new_cells = Vec::with_capacity(itc_mac + count);
-
-            // Copy boundaries before insertion point
-            new_boundaries.extend_from_slice(&tap.cell_boundaries[..=insert_at]);
-
-            // Copy cells before insertion point
-            if insert_at < tap.cell_properties.len() {
-                new_cells.extend_from_slice(&tap.cell_properties[..insert_at]);
-            }
+    fn handle_insert_cells(&self, tap: &mut TableProperties, data: &[u8]) {
+        let [index, count, ..] = *data else {
+            return;
+        };
+        let Ok(width) = read_i16_le(data, 2) else {
+            return;
+        };
+        let count = count as usize;
+        if tap.cell_boundaries.is_empty() {
+            tap.cell_boundaries.push(0);
+        }
 
-            // Insert new cells
-            for _i in 0..count {
-                let prev_boundary = new_boundaries.last().copied().unwrap_or(0);
-                new_boundaries.push(prev_boundary + width);
-                new_cells.push(CellProperties::default());
-            }
+        let insert_at = (index as usize)
+            .min(tap.cell_properties.len())
+            .min(tap.cell_boundaries.len() - 1);
 
-            // Copy remaining boundaries and cells
-            if insert_at < tap.cell_boundaries.len() {
-                new_boundaries.extend_from_slice(&tap.cell_boundaries[insert_at..]);
-            }
-            if insert_at < tap.cell_properties.len() {
-                new_cells.extend_from_slice(&tap.cell_properties[insert_at..]);
-            }
-
-            tap.cell_boundaries = new_boundaries;
-            tap.cell_properties = new_cells;
-            tap.cell_count = itc_mac + count;
+        // New cells start at the boundary of the cell they are inserted before,
+        // and the cells after them move right
+        let start = tap.cell_boundaries[insert_at];
+        let shift = width.saturating_mul(count as i16);
+        for boundary in &mut tap.cell_boundaries[insert_at + 1..] {
+            *boundary = boundary.saturating_add(shift);
         }
-
-        Ok(())
+        let new_boundaries =
+            (1..=count).map(|i| start.saturating_add(width.saturating_mul(i as i16)));
+        tap.cell_boundaries
+            .splice(insert_at + 1..insert_at + 1, new_boundaries);
+        tap.cell_properties.splice(
+            insert_at..insert_at,
+            std::iter::repeat_n(CellProperties::default(), count),
+        );
+        tap.cell_count = tap.cell_properties.len();
     }
 
     /// Parse cell padding (sprmTCellPaddingDefault - 0xD634).
@@ -478,31 +537,19 @@ impl<'arena> TapParser<'arena> {
     /// - byte 2: grfbrc (flags indicating which borders to apply padding to)
     /// - byte 3: ftsWidth (width type)
     /// - bytes 4-5: wWidth (padding width)
-    fn parse_cell_padding(
-        &self,
-        tap: &mut TableProperties,
-        sprm: &Sprm,
-        grpprl: &[u8],
-    ) -> Result<()> {
-        let offset = sprm.offset + 3; // Skip SPRM header
-        if offset + 6 > grpprl.len() {
+    fn parse_cell_padding(&self, tap: &mut TableProperties, data: &[u8]) -> Result<()> {
+        if data.len() < 6 {
             return Ok(());
         }
 
-        let itc_first = binary_to_doc_result(read_byte(grpprl, offset))? as usize;
-        let itc_lim = binary_to_doc_result(read_byte(grpprl, offset + 1))? as usize;
-        let grf_brc = binary_to_doc_result(read_byte(grpprl, offset + 2))?;
-        let _fts_width = binary_to_doc_result(read_byte(grpprl, offset + 3))?;
-        let w_width = binary_to_doc_result(read_u16_le(grpprl, offset + 4))? as i16;
+        let itc_first = binary_to_doc_result(read_byte(data, 0))?;
+        let itc_lim = binary_to_doc_result(read_byte(data, 1))?;
+        let grf_brc = binary_to_doc_result(read_byte(data, 2))?;
+        let _fts_width = binary_to_doc_result(read_byte(data, 3))?;
+        let w_width = binary_to_doc_result(read_u16_le(data, 4))? as i16;
 
         // Apply padding to specified cells
-        for c in itc_first..itc_lim {
-            if c >= tap.cell_properties.len() {
-                break;
-            }
-
-            let cell = &mut tap.cell_properties[c];
-
+        for cell in Self::cells_mut(tap, itc_first, itc_lim) {
             // Apply padding based on grfbrc flags
             if (grf_brc & 0x01) != 0 {
                 cell.padding_top = Some(w_width);
@@ -547,45 +594,37 @@ impl<'arena> TapParser<'arena> {
         }
     }
 
-    /// Parse cell shading (sprmTDefTableShd).
+    /// Parse cell shading (sprmTDefTableShd80).
     ///
-    /// This SPRM contains an array of ShadingDescriptor80 structures (2 bytes each),
-    /// one for each cell in the table.
-    ///
-    /// Based on Apache POI's table shading handling.
-    fn parse_cell_shading(
-        &self,
-        tap: &mut TableProperties,
-        sprm: &Sprm,
-        _grpprl: &[u8],
-    ) -> Result<()> {
-        let bytes = sprm.operand_bytes();
-        let shd_size = 2; // ShadingDescriptor80 is 2 bytes
-
-        // Parse shading descriptors for each cell
-        let num_shd = bytes.len() / shd_size;
-        for i in 0..num_shd.min(tap.cell_count) {
-            let offset = i * shd_size;
-            if offset + 1 < bytes.len()
-                && let Ok(shd) = read_u16_le(bytes, offset)
-            {
-                // Parse Shd80 structure
-                let ico_fore = (shd & 0x1F) as u8;
-                let ico_back = ((shd >> 5) & 0x1F) as u8;
-                let ipat = ((shd >> 10) & 0x3F) as u8;
-
-                // Apply shading to cell if pattern is not Clear
-                if ipat != 0 && i < tap.cell_properties.len() {
-                    let _fg_color = Self::ico_to_rgb(ico_fore);
-                    let _bg_color = Self::ico_to_rgb(ico_back);
-                    // Store shading info in cell properties if needed
-                    // For now, we mainly extract structure without applying
-                    let _ = ipat;
-                }
-            }
+    /// This SPRM contains an array of Shd80 structures (2 bytes each),
+    /// one for each cell in the row.
+    fn parse_cell_shading_80(&self, tap: &mut TableProperties, data: &[u8]) {
+        for (cell, shd) in tap.cell_properties.iter_mut().zip(data.chunks_exact(2)) {
+            let shd = u16::from_le_bytes([shd[0], shd[1]]);
+            let ico_fore = (shd & 0x1F) as u8;
+            let ico_back = ((shd >> 5) & 0x1F) as u8;
+            let ipat = ((shd >> 10) & 0x3F) as u8;
+
+            // A solid pattern shows the foreground color; otherwise the
+            // background color shows through, unless it is automatic
+            let ico = if ipat == 1 { ico_fore } else { ico_back };
+            cell.background_color = (ico != 0).then(|| Self::ico_to_rgb(ico));
         }
+    }
 
-        Ok(())
+    /// Parse cell shading (sprmTDefTableShd and its 2nd/3rd variants).
+    ///
+    /// This SPRM contains an array of Shd structures (10 bytes each) for
+    /// the cells starting at `first_cell`: cvFore and cvBack COLORREFs
+    /// followed by the pattern (ipat).
+    fn parse_cell_shading(&self, tap: &mut TableProperties, data: &[u8], first_cell: usize) {
+        let cells = tap.cell_properties.iter_mut().skip(first_cell);
+        for (cell, shd) in cells.zip(data.chunks_exact(10)) {
+            let ipat = u16::from_le_bytes([shd[8], shd[9]]);
+            // A COLORREF whose fAuto byte is 0xFF is the automatic color
+            let color = if ipat == 1 { &shd[0..4] } else { &shd[4..8] };
+            cell.background_color = (color[3] != 0xFF).then(|| (color[0], color[1], color[2]));
+        }
     }
 }
 
@@ -616,6 +655,78 @@ mod tests {
         assert_eq!(tap.cell_boundaries.len(), 3);
     }
 
+    #[test]
+    fn test_cell_descriptors_and_shading() {
+        let arena = Bump::new();
+        let parser = TapParser::new(&arena);
+
+        // sprmTDefTable with 2 cells and TC80 descriptors
+        let mut def = vec![0x02, 0x00, 0x00, 0x64, 0x00, 0xC8, 0x00];
+        // Cell 0: first of a horizontal merge, vertical restart, centered
+        let mut tc0 = vec![0u8; 20];
+        tc0[0..2].copy_from_slice(&(0x01u16 | (3 << 5) | (1 << 7)).to_le_bytes());
+        tc0[4..8].copy_from_slice(&[0x08, 0x01, 0x06, 0x00]); // single red top border
+        // Cell 1: merged into cell 0, continues the cell above
+        let mut tc1 = vec![0u8; 20];
+        tc1[0..2].copy_from_slice(&(0x02u16 | (1 << 5)).to_le_bytes());
+        tc1[4..8].copy_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]); // no border
+        def.extend(tc0);
+        def.extend(tc1);
+
+        let mut sprm_data = vec![0x08, 0xD6];
+        sprm_data.extend(((def.len() + 1) as u16).to_le_bytes());
+        sprm_data.extend(def);
+        // sprmTDefTableShd80: solid yellow, then a clear pattern on blue
+        sprm_data.extend([0x09, 0xD6, 0x04, 0x07, 0x04, 0x40, 0x00]);
+
+        let tap = parser.parse_tap(&sprm_data).unwrap();
+        let cells = &tap.cell_properties;
+        assert_eq!(cells.len(), 2);
+        assert_eq!(cells[0].merge_status, CellMergeStatus::First);
+        assert_eq!(cells[0].vertical_merge, CellMergeStatus::First);
+        assert_eq!(cells[0].vertical_alignment, VerticalAlignment::Center);
+        let top = cells[0].borders.top.unwrap();
+        assert_eq!(top.border_type, BorderType::Single);
+        assert_eq!(top.color, Some((255, 0, 0)));
+        assert_eq!(cells[1].merge_status, CellMergeStatus::Merged);
+        assert_eq!(cells[1].vertical_merge, CellMergeStatus::Merged);
+        assert!(cells[1].borders.top.is_none());
+        assert_eq!(cells[0].background_color, Some((255, 255, 0)));
+        assert_eq!(cells[1].background_color, Some((0, 0, 255)));
+    }
+
+    #[test]
+    fn test_range_sprms() {
+        let arena = Bump::new();
+        let parser = TapParser::new(&arena);
+
+        let sprm_data = vec![
+            0x08, 0xD6, 0x0A, 0x00, 0x03, 0x00, 0x00, 0x64, 0x00, 0xC8, 0x00, 0x2C,
+            0x01, // 3 cells
+            0x2B, 0xD6, 0x02, 0x02, 0x01, // sprmTVertMerge: cell 2 continues
+            0x2C, 0xD6, 0x03, 0x00, 0x02, 0x02, // sprmTVertAlign: cells 0-1 bottom
+            0x2F, 0xD6, 0x0B, 0x01, 0x03, 0x04, // sprmTSetBrc: cells 1-2, bottom edge
+            0x00, 0x80, 0x00, 0x00, 0x0C, 0x03, 0x00, 0x00, // green double 1.5pt
+            0x24, 0x56, 0x00, 0x02, // sprmTMerge: cells 0-1
+        ];
+
+        let tap = parser.parse_tap(&sprm_data).unwrap();
+        let cells = &tap.cell_properties;
+        assert_eq!(tap.cell_boundaries, vec![0, 100, 200, 300]);
+        assert_eq!(cells[2].vertical_merge, CellMergeStatus::Merged);
+        assert_eq!(cells[0].vertical_alignment, VerticalAlignment::Bottom);
+        assert_eq!(cells[2].vertical_alignment, VerticalAlignment::Top);
+        assert!(cells[0].borders.bottom.is_none());
+        let bottom = cells[1].borders.bottom.unwrap();
+        assert_eq!(bottom.width, 12);
+        assert_eq!(bottom.border_type, BorderType::Double);
+        assert_eq!(bottom.color, Some((0, 128, 0)));
+        assert!(cells[1].borders.top.is_none());
+        assert_eq!(cells[0].merge_status, CellMergeStatus::First);
+        assert_eq!(cells[1].merge_status, CellMergeStatus::Merged);
+        assert_eq!(cells[2].merge_status, CellMergeStatus::None);
+    }
+
     #[test]
     fn test_border_code_parsing() {
         let data = vec![
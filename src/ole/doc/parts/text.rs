@@ -4,14 +4,30 @@
 /// Text in DOC files is stored in a complex way:
 /// - The actual text bytes are in the WordDocument stream
 /// - A "Piece Table" (CLX structure) in the Table stream maps character positions to file positions
-/// - Text can be in either 8-bit (Windows-1252) or 16-bit (UTF-16LE) format
+/// - Text can be in either 8-bit (ANSI codepage) or 16-bit (UTF-16LE) format
+use std::borrow::Cow;
+
 use super::super::package::{DocError, Result};
+use super::chp::CharacterProperties;
+use super::chp_bin_table::ChpBinTable;
 use super::fib::FileInformationBlock;
+use super::font_table::{FontEntry, FontTable};
 use crate::common::binary::{read_u16_le, read_u32_le};
+use crate::common::encoding::{
+    codepage_to_encoding, is_double_byte_codepage, lid_to_codepage, symbol_font_char,
+};
+use crate::common::trace;
 
 /// Size of a PieceDescriptor in bytes (8 bytes as per Apache POI)
 pub const PIECE_DESCRIPTOR_SIZE: usize = 8;
 
+/// Placeholder for the trail byte of a double-byte character.
+///
+/// The extracted text keeps one character per CP so that CP ranges can be
+/// sliced by character index; a double-byte character in 8-bit text spans
+/// two CPs, so its second CP holds this placeholder.
+pub(crate) const CP_PADDING: char = '\0';
+
 /// CLX (Compound Line Extension) parsing utilities.
 ///
 /// Based on Apache POI's PlexOfCps implementation, the CLX structure is a
@@ -24,10 +40,20 @@ pub const PIECE_DESCRIPTOR_SIZE: usize = 8;
 ///
 /// Handles the complex text extraction process from DOC binary structures.
 pub struct TextExtractor {
-    /// The extracted text
+    /// The extracted text, one character per CP
     text: String,
 }
 
+/// What 8-bit text and symbol characters are decoded with.
+pub(crate) struct TextDecoding<'a> {
+    /// Codepage of 8-bit text in fonts without a character set of their own
+    codepage: u32,
+    /// Character runs, to find the font of each CP
+    chp_bin_table: Option<&'a ChpBinTable>,
+    /// Font table, indexed by the runs' ftc
+    fonts: Option<&'a FontTable>,
+}
+
 impl TextExtractor {
     /// Create a new TextExtractor and extract text.
     ///
@@ -41,18 +67,47 @@ impl TextExtractor {
         word_document: &[u8],
         table_stream: &[u8],
     ) -> Result<Self> {
+        Self::with_fonts(fib, word_document, table_stream, None, None)
+    }
+
+    /// Create a new TextExtractor that decodes text with the fonts of its runs.
+    ///
+    /// 8-bit text is decoded in the codepage of the run's font character set,
+    /// or else in the codepage of the document language from the FIB. Text in
+    /// symbol fonts (Symbol, Wingdings) is mapped to its Unicode equivalents.
+    ///
+    /// # Arguments
+    ///
+    /// * `fib` - The File Information Block
+    /// * `word_document` - The WordDocument stream
+    /// * `table_stream` - The Table stream (0Table or 1Table)
+    /// * `chp_bin_table` - Character runs, if parsed
+    /// * `fonts` - Font table, if present
+    pub fn with_fonts(
+        fib: &FileInformationBlock,
+        word_document: &[u8],
+        table_stream: &[u8],
+        chp_bin_table: Option<&ChpBinTable>,
+        fonts: Option<&FontTable>,
+    ) -> Result<Self> {
+        let decoding = TextDecoding::new(fib, chp_bin_table, fonts);
+
         // Extract text using the piece table
-        let text = Self::extract_text_from_pieces(fib, word_document, table_stream)?;
+        let text = Self::extract_text_from_pieces(fib, word_document, table_stream, &decoding)?;
 
         Ok(Self { text })
     }
 
     /// Extract all text from the document.
     pub fn extract_all_text(&self) -> Result<String> {
-        Ok(self.text.clone())
+        Ok(strip_padding(&self.text).into_owned())
     }
 
     /// Get a reference to the full extracted text.
+    ///
+    /// The text has one character per CP, so the trail bytes of double-byte
+    /// characters are kept as [`CP_PADDING`]; use
+    /// [`extract_all_text`](Self::extract_all_text) for display text.
     #[inline]
     pub fn text(&self) -> &str {
         &self.text
@@ -70,21 +125,21 @@ impl TextExtractor {
     ///
     /// # Returns
     ///
-    /// The text slice for the given CP range, or an empty string if out of bounds.
-    pub fn text_at_range(&self, start_cp: u32, end_cp: u32) -> &str {
+    /// The text for the given CP range, or an empty string if out of bounds.
+    pub fn text_at_range(&self, start_cp: u32, end_cp: u32) -> Cow<'_, str> {
         let chars: Vec<char> = self.text.chars().collect();
         let start = start_cp as usize;
         let end = (end_cp as usize).min(chars.len());
 
         if start >= end || start >= chars.len() {
-            return "";
+            return Cow::Borrowed("");
         }
 
         // Convert character indices to byte offsets
         let byte_start: usize = chars[..start].iter().map(|c| c.len_utf8()).sum();
         let byte_end: usize = chars[..end].iter().map(|c| c.len_utf8()).sum();
 
-        &self.text[byte_start..byte_end]
+        strip_padding(&self.text[byte_start..byte_end])
     }
 
     /// Extract text using the piece table (CLX structure).
@@ -96,6 +151,7 @@ impl TextExtractor {
         fib: &FileInformationBlock,
         word_document: &[u8],
         table_stream: &[u8],
+        decoding: &TextDecoding,
     ) -> Result<String> {
         // Get the CLX (piece table) location from FIB
         // CLX is at FibRgFcLcb index 33 (fcClx, lcbClx) according to Apache POI's FIBFieldHandler
@@ -133,7 +189,13 @@ impl TextExtractor {
         let clx_data = &table_stream[clx_offset..clx_offset + clx_length];
 
         // Try to parse the piece table from CLX
-        match Self::parse_piece_table(clx_data, word_document) {
+        let text = Self::parse_piece_table(clx_data).and_then(|pieces| match pieces {
+            Some(pieces) => {
+                Self::extract_text_from_piece_descriptors(&pieces, word_document, decoding)
+            },
+            None => Ok(String::new()),
+        });
+        match text {
             Ok(text) if !text.is_empty() => Ok(text),
             _ => {
                 // If CLX parsing fails or returns empty, fall back to simple text extraction
@@ -149,7 +211,9 @@ impl TextExtractor {
     /// - TEXT_PIECE_TABLE_TYPE marker (0x02)
     /// - 4-byte size of the piece table data
     /// - The piece table data itself (PlexOfCps structure)
-    fn parse_piece_table(clx_data: &[u8], word_document: &[u8]) -> Result<String> {
+    ///
+    /// Returns `None` when the CLX has no piece table.
+    fn parse_piece_table(clx_data: &[u8]) -> Result<Option<Vec<PieceDescriptor>>> {
         let mut offset = 0;
 
         // Skip GRPPR L sections (type 0x01) until we find the piece table
@@ -193,10 +257,7 @@ impl TextExtractor {
                     let piece_table_data = &clx_data[offset..offset + piece_table_size];
 
                     // Parse the piece table using PlexOfCps logic
-                    let pieces = Self::parse_plex_of_cps(piece_table_data)?;
-
-                    // Extract text from the parsed pieces
-                    return Self::extract_text_from_piece_descriptors(&pieces, word_document);
+                    return Self::parse_plex_of_cps(piece_table_data).map(Some);
                 },
                 0x14 => {
                     // Document Properties Descriptor - contains document-wide properties
@@ -225,8 +286,29 @@ impl TextExtractor {
         }
 
         // If we reach here, no piece table was found in the CLX
-        // Return empty string to trigger fallback
-        Ok(String::new())
+        // Return None to trigger fallback
+        Ok(None)
+    }
+
+    /// Get the text pieces of a document whose text is stored in a piece table.
+    ///
+    /// Returns `None` when the document has no piece table, in which case
+    /// its text is read without one.
+    pub(crate) fn pieces(
+        fib: &FileInformationBlock,
+        table_stream: &[u8],
+    ) -> Result<Option<Vec<PieceDescriptor>>> {
+        let Some((clx_offset, clx_length)) = fib.get_table_pointer(33) else {
+            return Ok(None);
+        };
+        let start = clx_offset as usize;
+        let Some(clx_data) = table_stream.get(start..start + clx_length as usize) else {
+            return Ok(None);
+        };
+        if clx_data.is_empty() {
+            return Ok(None);
+        }
+        Self::parse_piece_table(clx_data)
     }
 
     /// Parse a PlexOfCps structure (Property List with Character Positions).
@@ -316,12 +398,13 @@ impl TextExtractor {
     fn extract_text_from_piece_descriptors(
         pieces: &[PieceDescriptor],
         word_document: &[u8],
+        decoding: &TextDecoding,
     ) -> Result<String> {
         let mut text = String::new();
 
         for piece in pieces {
             // Calculate text length in characters from CP range
-            let char_count = (piece.cp_end - piece.cp_start) as usize;
+            let char_count = piece.cp_end.saturating_sub(piece.cp_start) as usize;
 
             if char_count == 0 {
                 continue; // Empty piece
@@ -339,20 +422,20 @@ impl TextExtractor {
             let end = start + byte_count;
 
             if start >= word_document.len() {
-                eprintln!(
-                    "Warning: Piece file position {} beyond document length {}",
+                trace::warn!(
                     start,
-                    word_document.len()
+                    doc_len = word_document.len(),
+                    "skipping text piece that starts beyond the document"
                 );
                 continue;
             }
 
             if end > word_document.len() {
-                eprintln!(
-                    "Warning: Piece extends beyond document: start={}, end={}, doc_len={}",
+                trace::warn!(
                     start,
                     end,
-                    word_document.len()
+                    doc_len = word_document.len(),
+                    "text piece extends beyond the document, truncating it"
                 );
                 // Try to read what we can
                 let available_end = word_document.len();
@@ -364,27 +447,7 @@ impl TextExtractor {
             let actual_end = end.min(word_document.len());
             let text_data = &word_document[start..actual_end];
 
-            if piece.is_ansi {
-                // 8-bit ANSI text (Windows-1252)
-                for &byte in text_data {
-                    text.push(windows_1252_to_char(byte));
-                }
-            } else {
-                // 16-bit Unicode (UTF-16LE)
-                // Make sure we have complete UTF-16LE pairs
-                let utf16_data = if text_data.len().is_multiple_of(2) {
-                    text_data
-                } else {
-                    &text_data[..text_data.len() & !1] // Truncate to even length
-                };
-
-                for chunk in utf16_data.chunks_exact(2) {
-                    let code_unit = read_u16_le(chunk, 0).unwrap_or(0);
-                    if let Some(ch) = char::from_u32(code_unit as u32) {
-                        text.push(ch);
-                    }
-                }
-            }
+            decoding.decode_piece(piece, piece.cp_start, piece.cp_end, text_data, &mut text);
         }
 
         Ok(text)
@@ -420,15 +483,187 @@ impl TextExtractor {
 ///
 /// Maps a range of character positions to a location in the WordDocument stream.
 #[derive(Debug, Clone)]
-struct PieceDescriptor {
+pub(crate) struct PieceDescriptor {
     /// Starting character position
-    cp_start: u32,
+    pub(crate) cp_start: u32,
     /// Ending character position
-    cp_end: u32,
+    pub(crate) cp_end: u32,
     /// File position in WordDocument stream
-    file_pos: usize,
+    pub(crate) file_pos: usize,
     /// Whether text is ANSI (true) or Unicode (false)
-    is_ansi: bool,
+    pub(crate) is_ansi: bool,
+}
+
+impl PieceDescriptor {
+    /// Get the number of bytes each character of the piece takes.
+    #[inline]
+    pub(crate) fn char_size(&self) -> usize {
+        if self.is_ansi { 1 } else { 2 }
+    }
+}
+
+impl<'a> TextDecoding<'a> {
+    /// Decode text with the language of `fib` and the fonts of its runs.
+    pub(crate) fn new(
+        fib: &FileInformationBlock,
+        chp_bin_table: Option<&'a ChpBinTable>,
+        fonts: Option<&'a FontTable>,
+    ) -> Self {
+        Self {
+            codepage: lid_to_codepage(fib.language_id()),
+            chp_bin_table,
+            fonts,
+        }
+    }
+
+    /// Decode the CPs `cp_start..cp_end` of `piece` into `text`.
+    ///
+    /// `text_data` holds the bytes of the piece from `cp_start` on; it may
+    /// be short when the piece runs past the end of the WordDocument stream.
+    pub(crate) fn decode_piece(
+        &self,
+        piece: &PieceDescriptor,
+        cp_start: u32,
+        cp_end: u32,
+        text_data: &[u8],
+        text: &mut String,
+    ) {
+        // Decode each stretch of the piece with the font of its run
+        for (segment_start_cp, segment_end_cp, properties) in self.segments(cp_start, cp_end) {
+            let offset = (segment_start_cp - cp_start) as usize;
+            let len = (segment_end_cp - segment_start_cp) as usize;
+            let font = properties
+                .and_then(|chp| chp.font_index)
+                .and_then(|ftc| self.font(ftc));
+            let segment_start = text.len();
+
+            if piece.is_ansi {
+                // 8-bit ANSI text in the font or document codepage
+                let end = (offset + len).min(text_data.len());
+                if let Some(bytes) = text_data.get(offset..end) {
+                    self.decode_ansi(bytes, font, text);
+                }
+            } else {
+                // 16-bit Unicode (UTF-16LE)
+                let end = ((offset + len) * 2).min(text_data.len() & !1);
+                if let Some(utf16_data) = text_data.get(offset * 2..end) {
+                    for chunk in utf16_data.chunks_exact(2) {
+                        let code_unit = read_u16_le(chunk, 0).unwrap_or(0);
+                        if let Some(ch) = char::from_u32(code_unit as u32) {
+                            text.push(map_symbol(font, ch));
+                        }
+                    }
+                }
+            }
+
+            // Special characters inserted from a symbol font (sprmCSymbol)
+            // are stored as placeholders
+            if let Some((ftc, code)) = properties.and_then(|chp| chp.symbol) {
+                let ch = char::from_u32(code as u32).unwrap_or(char::REPLACEMENT_CHARACTER);
+                let symbol = map_symbol(self.font(ftc), ch);
+                let count = text[segment_start..].chars().count();
+                text.truncate(segment_start);
+                text.extend(std::iter::repeat_n(symbol, count));
+            }
+        }
+    }
+
+    /// Split a CP range into stretches with the same character properties.
+    ///
+    /// Stretches outside any run have no properties.
+    fn segments(
+        &self,
+        cp_start: u32,
+        cp_end: u32,
+    ) -> Vec<(u32, u32, Option<&CharacterProperties>)> {
+        let mut segments = Vec::new();
+        let mut cp = cp_start;
+
+        if let Some(chp_bin_table) = self.chp_bin_table {
+            for run in chp_bin_table.runs_in_range(cp_start, cp_end) {
+                let start = run.start_cp.max(cp);
+                let end = run.end_cp.min(cp_end);
+                if start >= end {
+                    continue;
+                }
+                if start > cp {
+                    segments.push((cp, start, None));
+                }
+                segments.push((start, end, Some(&run.properties)));
+                cp = end;
+            }
+        }
+
+        if cp < cp_end {
+            segments.push((cp, cp_end, None));
+        }
+        segments
+    }
+
+    /// Look up a font by its index (ftc).
+    fn font(&self, ftc: u16) -> Option<&FontEntry> {
+        self.fonts.and_then(|fonts| fonts.get(ftc))
+    }
+
+    /// Decode 8-bit text, one character per byte.
+    ///
+    /// Double-byte characters are followed by [`CP_PADDING`] for their trail
+    /// byte.
+    fn decode_ansi(&self, bytes: &[u8], font: Option<&FontEntry>, text: &mut String) {
+        if font.is_some_and(FontEntry::is_symbol) {
+            text.extend(bytes.iter().map(|&byte| map_symbol(font, char::from(byte))));
+            return;
+        }
+
+        let codepage = font.and_then(FontEntry::codepage).unwrap_or(self.codepage);
+        let Some(encoding) = codepage_to_encoding(codepage) else {
+            text.extend(bytes.iter().map(|&byte| windows_1252_to_char(byte)));
+            return;
+        };
+
+        if !is_double_byte_codepage(codepage) {
+            // Single-byte codepages map every byte to one character
+            let (decoded, _) = encoding.decode_without_bom_handling(bytes);
+            text.push_str(&decoded);
+            return;
+        }
+
+        let mut i = 0;
+        while i < bytes.len() {
+            if bytes[i] >= 0x80
+                && let Some(pair) = bytes.get(i..i + 2)
+                && let Some(decoded) =
+                    encoding.decode_without_bom_handling_and_without_replacement(pair)
+            {
+                let mut chars = decoded.chars();
+                if let (Some(ch), None) = (chars.next(), chars.next()) {
+                    text.push(ch);
+                    text.push(CP_PADDING);
+                    i += 2;
+                    continue;
+                }
+            }
+            let (decoded, _) = encoding.decode_without_bom_handling(&bytes[i..i + 1]);
+            text.push_str(&decoded);
+            i += 1;
+        }
+    }
+}
+
+/// Map a character of a symbol font to its Unicode equivalent, if any.
+fn map_symbol(font: Option<&FontEntry>, ch: char) -> char {
+    font.filter(|font| font.is_symbol())
+        .and_then(|font| symbol_font_char(&font.name, ch))
+        .unwrap_or(ch)
+}
+
+/// Remove the trail byte placeholders of double-byte characters.
+fn strip_padding(text: &str) -> Cow<'_, str> {
+    if text.contains(CP_PADDING) {
+        Cow::Owned(text.replace(CP_PADDING, ""))
+    } else {
+        Cow::Borrowed(text)
+    }
 }
 
 /// Convert a Windows-1252 byte to a Unicode character.
@@ -480,6 +715,28 @@ mod tests {
         assert_eq!(windows_1252_to_char(0x94), '"');
     }
 
+    #[test]
+    fn test_decode_double_byte_text() {
+        let decoding = TextDecoding {
+            codepage: 936,
+            chp_bin_table: None,
+            fonts: None,
+        };
+        let mut text = String::new();
+        // "你好!" in GBK: each Chinese character takes two bytes and two CPs
+        decoding.decode_ansi(b"\xC4\xE3\xBA\xC3!", None, &mut text);
+        assert_eq!(text, "你\0好\0!");
+        assert_eq!(strip_padding(&text), "你好!");
+
+        let symbol = FontEntry {
+            name: "Symbol".to_string(),
+            charset: 2,
+        };
+        text.clear();
+        decoding.decode_ansi(b"\xB7a", Some(&symbol), &mut text);
+        assert_eq!(text, "•α");
+    }
+
     #[test]
     fn test_clx_parsing_structure() {
         // Test that the CLX structure constants are correct
@@ -0,0 +1,183 @@
+/// Text box parser for Word binary format.
+///
+/// Based on the [MS-DOC] PlcfSpa and PlcftxbxTxt structures. Shapes anchored
+/// in the main document are listed in PlcfSpa with their shape id (FSPA.spid);
+/// the text of text boxes lives in the text box subdocument, split into
+/// stories by PlcftxbxTxt whose FTXBXS entries name the owning shape (lid).
+use super::super::package::Result;
+use super::fib::FileInformationBlock;
+use crate::ole::plcf::PlcfParser;
+
+/// Size of an FSPA structure in PlcfSpa
+const FSPA_SIZE: usize = 26;
+/// Size of an FTXBXS structure in PlcftxbxTxt
+const FTXBXS_SIZE: usize = 22;
+
+/// A text box anchored in the main document
+#[derive(Debug, Clone)]
+pub struct TextBoxReference {
+    /// Character position of the shape anchor in the main document
+    pub anchor_cp: u32,
+    /// Shape identifier (FSPA.spid) of the text box
+    pub shape_id: u32,
+    /// Character position range of the text box story
+    pub text_start_cp: u32,
+    pub text_end_cp: u32,
+}
+
+/// Text boxes table parser
+pub struct TextBoxesTable {
+    /// Text boxes in anchor order
+    references: Vec<TextBoxReference>,
+}
+
+impl TextBoxesTable {
+    /// Parse the main document text boxes from the FIB and table stream
+    ///
+    /// # Arguments
+    ///
+    /// * `fib` - File Information Block
+    /// * `table_stream` - The table stream (0Table or 1Table)
+    ///
+    /// # Returns
+    ///
+    /// A parsed TextBoxesTable
+    pub fn parse(fib: &FileInformationBlock, table_stream: &[u8]) -> Result<Self> {
+        let mut references = Vec::new();
+
+        if let Some((subdoc_start, _subdoc_end)) = fib.get_textbox_range()
+            // FIB index 40: fcPlcSpaMom and lcbPlcSpaMom
+            && let Some(spa_data) = Self::table_slice(fib, table_stream, 40)
+            // FIB index 56: fcPlcftxbxTxt and lcbPlcftxbxTxt
+            && let Some(txbx_data) = Self::table_slice(fib, table_stream, 56)
+        {
+            references = Self::parse_plcfs(spa_data, txbx_data, subdoc_start);
+        }
+
+        Ok(Self { references })
+    }
+
+    /// Get the bytes of a PLCF in the table stream
+    fn table_slice<'a>(
+        fib: &FileInformationBlock,
+        table_stream: &'a [u8],
+        index: usize,
+    ) -> Option<&'a [u8]> {
+        let (offset, length) = fib.get_table_pointer(index)?;
+        let start = offset as usize;
+        if length == 0 || start >= table_stream.len() {
+            return None;
+        }
+        let end = start
+            .saturating_add(length as usize)
+            .min(table_stream.len());
+        Some(&table_stream[start..end])
+    }
+
+    /// Match the shapes of PlcfSpa with the text box stories of PlcftxbxTxt
+    fn parse_plcfs(spa_data: &[u8], txbx_data: &[u8], subdoc_start: u32) -> Vec<TextBoxReference> {
+        let (Some(spa_plcf), Some(txbx_plcf)) = (
+            PlcfParser::parse(spa_data, FSPA_SIZE),
+            PlcfParser::parse(txbx_data, FTXBXS_SIZE),
+        ) else {
+            return Vec::new();
+        };
+
+        // Story ranges by owning shape id. Reusable entries (fReusable) are
+        // free slots, not text boxes.
+        let stories: Vec<(u32, u32, u32)> = (0..txbx_plcf.count())
+            .filter_map(|i| {
+                let (start, end) = txbx_plcf.range(i)?;
+                let ftxbxs = txbx_plcf.property(i)?;
+                let reusable = crate::common::binary::read_u16_le(ftxbxs, 8).ok()?;
+                let lid = crate::common::binary::read_u32_le(ftxbxs, 14).ok()?;
+                (reusable == 0).then_some((lid, start, end))
+            })
+            .collect();
+
+        let mut references = Vec::new();
+        for i in 0..spa_plcf.count() {
+            if let Some(anchor_cp) = spa_plcf.position(i)
+                && let Some(fspa) = spa_plcf.property(i)
+                && let Ok(shape_id) = crate::common::binary::read_u32_le(fspa, 0)
+                && let Some(&(_, start, end)) = stories.iter().find(|(lid, ..)| *lid == shape_id)
+            {
+                references.push(TextBoxReference {
+                    anchor_cp,
+                    shape_id,
+                    text_start_cp: subdoc_start.saturating_add(start),
+                    text_end_cp: subdoc_start.saturating_add(end),
+                });
+            }
+        }
+
+        references
+    }
+
+    /// Get all text box references
+    pub fn references(&self) -> &[TextBoxReference] {
+        &self.references
+    }
+
+    /// Get the count of text boxes
+    pub fn count(&self) -> usize {
+        self.references.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a PLCF from CPs and fixed-size elements
+    fn plcf(cps: &[u32], elements: &[Vec<u8>]) -> Vec<u8> {
+        let mut data: Vec<u8> = cps.iter().flat_map(|cp| cp.to_le_bytes()).collect();
+        for element in elements {
+            data.extend_from_slice(element);
+        }
+        data
+    }
+
+    fn fspa(spid: u32) -> Vec<u8> {
+        let mut data = vec![0u8; FSPA_SIZE];
+        data[..4].copy_from_slice(&spid.to_le_bytes());
+        data
+    }
+
+    fn ftxbxs(lid: u32, reusable: bool) -> Vec<u8> {
+        let mut data = vec![0u8; FTXBXS_SIZE];
+        data[8..10].copy_from_slice(&u16::from(reusable).to_le_bytes());
+        data[14..18].copy_from_slice(&lid.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_text_boxes_matched_by_shape_id() {
+        // A picture (2049) and two text boxes anchored at CPs 10, 25 and 40
+        let spa = plcf(&[10, 25, 40, 60], &[fspa(2049), fspa(2050), fspa(2051)]);
+        // Stories in a different order than the anchors, then the final slot
+        let txbx = plcf(
+            &[0, 12, 20, 22],
+            &[ftxbxs(2051, false), ftxbxs(2050, false), ftxbxs(0, true)],
+        );
+
+        let references = TextBoxesTable::parse_plcfs(&spa, &txbx, 1000);
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].anchor_cp, 25);
+        assert_eq!(references[0].shape_id, 2050);
+        assert_eq!(
+            (references[0].text_start_cp, references[0].text_end_cp),
+            (1012, 1020)
+        );
+        assert_eq!(references[1].anchor_cp, 40);
+        assert_eq!(
+            (references[1].text_start_cp, references[1].text_end_cp),
+            (1000, 1012)
+        );
+    }
+
+    #[test]
+    fn test_text_boxes_malformed_plcf() {
+        assert!(TextBoxesTable::parse_plcfs(&[1, 2, 3], &[], 0).is_empty());
+    }
+}
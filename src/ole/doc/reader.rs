@@ -0,0 +1,378 @@
+//! Incremental reader of the main document text of a Word document.
+//!
+//! Instead of loading the whole WordDocument stream, the reader decodes the
+//! main document a stretch of paragraphs at a time, reading only the text of
+//! those paragraphs and the FKP pages that format them.
+
+use super::document::Document;
+use super::package::Result;
+use super::paragraph::{Paragraph, Run};
+use super::parts::chp_bin_table::ChpBinTable;
+use super::parts::fib::FileInformationBlock;
+use super::parts::fkp::fkp_pages;
+use super::parts::font_table::FontTable;
+use super::parts::pap_bin_table::PapBinTable;
+use super::parts::paragraph_extractor::ParagraphExtractor;
+use super::parts::piece_table::PieceTable;
+use super::parts::text::{PieceDescriptor, TextDecoding, TextExtractor};
+use crate::document::DocumentElement;
+use crate::ole::{OleFile, StreamLocation};
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Number of CPs decoded at a time; a stretch grows until it ends a paragraph
+const STRETCH_CPS: u32 = 16 * 1024;
+
+/// Size of an FKP page in the WordDocument stream
+const FKP_PAGE_SIZE: usize = 512;
+
+/// Reads the paragraphs and tables of the main document a few at a time.
+///
+/// Each call to [`BodyReader::next_elements`] reads only the text of the
+/// next stretch of paragraphs, so a caller that stops early never reads the
+/// rest of the text.
+pub(crate) struct BodyReader {
+    /// Location of the WordDocument stream
+    location: StreamLocation,
+    /// File Information Block, read from the start of the WordDocument stream
+    fib: FileInformationBlock,
+    /// The table stream (0Table or 1Table)
+    table_stream: Vec<u8>,
+    /// Piece table for FC-to-CP conversion of the FKP runs
+    piece_table: Option<PieceTable>,
+    /// Text pieces, in CP order
+    pieces: Vec<PieceDescriptor>,
+    /// Font table, to decode 8-bit text
+    fonts: Option<FontTable>,
+    /// FKP pages of the character property bin table
+    chp_pages: Option<BinTablePages>,
+    /// FKP pages of the paragraph property bin table
+    pap_pages: Option<BinTablePages>,
+    /// FKP pages read so far, by page number
+    pages: HashMap<u32, Vec<u8>>,
+    /// CP of the next character to read
+    cp: u32,
+    /// Index of the next character in the decoded text
+    text_cp: u32,
+    /// CP where the main document ends
+    end_cp: u32,
+    /// Paragraphs of a table that may go on in the next stretch
+    pending: Vec<Paragraph>,
+}
+
+/// The FKP pages of a bin table and how many of them have been read.
+struct BinTablePages {
+    /// Location of the PlcfBte in the table stream
+    plcf: Range<usize>,
+    /// First FC and page number of each page, in FC order
+    pages: Vec<(u32, u32)>,
+    /// Number of pages, from the first, that have been read
+    loaded: usize,
+}
+
+/// Text of one piece within a stretch, as stored in the WordDocument stream.
+struct TextPart {
+    /// Index of the piece
+    piece: usize,
+    /// CP of the first character of `bytes`
+    cp_start: u32,
+    /// Raw text bytes
+    bytes: Vec<u8>,
+}
+
+impl BodyReader {
+    /// Prepare to read the main document of a Word document.
+    ///
+    /// Returns `None` when the document can't be read incrementally, e.g.
+    /// when it predates Word 97, has no piece table or has its text pieces
+    /// out of order; callers then load the whole document.
+    pub(crate) fn open<R: Read + Seek>(ole: &mut OleFile<R>) -> Result<Option<Self>> {
+        let Ok(location) = ole.stream_location(&["WordDocument"]) else {
+            return Ok(None);
+        };
+        let Ok(fib) = FileInformationBlock::read(ole) else {
+            return Ok(None);
+        };
+        // Word 6.0 and 95 FIBs have a different layout
+        if fib.version() < 0x00C1 {
+            return Ok(None);
+        }
+
+        let table_stream_name = if fib.which_table_stream() {
+            "1Table"
+        } else {
+            "0Table"
+        };
+        let Ok(table_stream) = ole.open_stream(&[table_stream_name]) else {
+            return Ok(None);
+        };
+
+        let Ok(Some(pieces)) = TextExtractor::pieces(&fib, &table_stream) else {
+            return Ok(None);
+        };
+        // Stretches of text are matched to FKP pages by file position, which
+        // needs pieces stored in the same order as their CPs
+        let in_order = pieces.windows(2).all(|pair| {
+            let length = (pair[0].cp_end - pair[0].cp_start) as usize * pair[0].char_size();
+            pair[0].cp_end <= pair[1].cp_start && pair[0].file_pos + length <= pair[1].file_pos
+        });
+        if pieces.is_empty()
+            || !in_order
+            || pieces.iter().any(|piece| piece.cp_start > piece.cp_end)
+        {
+            return Ok(None);
+        }
+
+        // Index 33 in FibRgFcLcb97 is fcClx/lcbClx (the piece table)
+        let piece_table = fib.get_table_pointer(33).and_then(|(offset, length)| {
+            let start = offset as usize;
+            let end = start
+                .saturating_add(length as usize)
+                .min(table_stream.len());
+            (length > 0 && start < end)
+                .then(|| PieceTable::parse(&table_stream[start..end]))
+                .flatten()
+        });
+
+        // Index 12 is fcPlcfBteChpx/lcbPlcfBteChpx and index 13 is
+        // fcPlcfBtePapx/lcbPlcfBtePapx; both need the piece table
+        let bin_table = |index: usize, min_len: usize| {
+            piece_table.as_ref()?;
+            let (offset, length) = fib.get_table_pointer(index)?;
+            let start = offset as usize;
+            let end = start
+                .saturating_add(length as usize)
+                .min(table_stream.len());
+            (length > 0 && start < end && end - start >= min_len).then(|| BinTablePages {
+                pages: fkp_pages(&table_stream[start..end]),
+                plcf: start..end,
+                loaded: 0,
+            })
+        };
+        let chp_pages = bin_table(12, 8);
+        let pap_pages = bin_table(13, 1);
+
+        let fonts = FontTable::parse(&fib, &table_stream);
+        let (cp, end_cp) = fib.get_main_doc_range();
+
+        Ok(Some(Self {
+            location,
+            fib,
+            table_stream,
+            piece_table,
+            pieces,
+            fonts,
+            chp_pages,
+            pap_pages,
+            pages: HashMap::new(),
+            cp,
+            text_cp: cp,
+            end_cp,
+            pending: Vec::new(),
+        }))
+    }
+
+    /// Read the next paragraphs and tables of the main document.
+    ///
+    /// Returns `None` once the whole main document has been read.
+    pub(crate) fn next_elements<R: Read + Seek>(
+        &mut self,
+        ole: &mut OleFile<R>,
+    ) -> Result<Option<Vec<DocumentElement>>> {
+        while self.cp < self.end_cp {
+            let paragraphs = self.read_paragraphs(ole)?;
+            self.pending.extend(paragraphs);
+
+            // A table may go on in the next stretch, so only the paragraphs
+            // up to the last one outside a table are grouped
+            let Some(last) = self
+                .pending
+                .iter()
+                .rposition(|para| !para.properties().in_table)
+            else {
+                continue;
+            };
+            let rest = self.pending.split_off(last + 1);
+            let ready = std::mem::replace(&mut self.pending, rest);
+            let elements = Document::group_elements(&ready)?;
+            if !elements.is_empty() {
+                return Ok(Some(elements));
+            }
+        }
+
+        if self.pending.is_empty() {
+            return Ok(None);
+        }
+        Document::group_elements(&std::mem::take(&mut self.pending)).map(Some)
+    }
+
+    /// Read the paragraphs of the next stretch of text.
+    ///
+    /// A stretch ends with a paragraph mark, or at the end of the main
+    /// document.
+    fn read_paragraphs<R: Read + Seek>(&mut self, ole: &mut OleFile<R>) -> Result<Vec<Paragraph>> {
+        let start = self.cp;
+        let mut end = start.saturating_add(STRETCH_CPS).min(self.end_cp);
+        let (end, parts) = loop {
+            let parts = self.read_text(ole, start, end)?;
+            if end == self.end_cp {
+                break (end, parts);
+            }
+            // Paragraph marks are single bytes that can't be the trail byte
+            // of a double-byte character, so the text can be split after them
+            if let Some(mark) = Self::last_paragraph_mark(&self.pieces, &parts) {
+                break (mark + 1, parts);
+            }
+            end = start
+                .saturating_add((end - start).saturating_mul(2))
+                .min(self.end_cp);
+        };
+        self.cp = end;
+
+        // The FKP pages up to the end of the stretch cover its runs
+        let fc_end = parts
+            .iter()
+            .map(|part| {
+                let piece = &self.pieces[part.piece];
+                piece.file_pos
+                    + (part.cp_start - piece.cp_start) as usize * piece.char_size()
+                    + part.bytes.len()
+            })
+            .max()
+            .unwrap_or(0);
+        self.load_pages(ole, fc_end as u64)?;
+
+        let (chp_bin_table, pap_bin_table) = self.bin_tables();
+        let decoding = TextDecoding::new(&self.fib, chp_bin_table.as_ref(), self.fonts.as_ref());
+        let mut text = String::new();
+        for part in &parts {
+            let piece = &self.pieces[part.piece];
+            let part_end = piece.cp_end.min(end);
+            if part.cp_start < part_end {
+                decoding.decode_piece(piece, part.cp_start, part_end, &part.bytes, &mut text);
+            }
+        }
+
+        // Characters are numbered by their index in the decoded text, as
+        // when the whole text is decoded at once
+        let text_start = self.text_cp;
+        let text_end = text_start + text.chars().count() as u32;
+        self.text_cp = text_end;
+        if text_start == text_end {
+            return Ok(Vec::new());
+        }
+
+        let extractor = ParagraphExtractor::new_with_range(
+            &self.fib,
+            &self.table_stream,
+            Arc::new(text),
+            chp_bin_table.as_ref(),
+            (text_start, text_end),
+        )?
+        .with_pap_bin_table(pap_bin_table.as_ref())
+        .with_text_start_cp(text_start);
+
+        Ok(extractor
+            .extract_paragraphs()?
+            .into_iter()
+            .map(|(_, properties, runs)| {
+                let mut para = Paragraph::new(String::new());
+                para.set_runs(
+                    runs.into_iter()
+                        .map(|(text, props)| Run::new(text, props))
+                        .collect(),
+                );
+                para.set_properties(properties);
+                para
+            })
+            .collect())
+    }
+
+    /// Read the raw text of the CPs `start..end`, piece by piece.
+    fn read_text<R: Read + Seek>(
+        &self,
+        ole: &mut OleFile<R>,
+        start: u32,
+        end: u32,
+    ) -> Result<Vec<TextPart>> {
+        let mut parts = Vec::new();
+        for (index, piece) in self.pieces.iter().enumerate() {
+            let cp_start = piece.cp_start.max(start);
+            let cp_end = piece.cp_end.min(end);
+            // Pieces that start past the stream have no text
+            if cp_start >= cp_end || piece.file_pos as u64 >= self.location.size() {
+                continue;
+            }
+
+            let offset = piece.file_pos + (cp_start - piece.cp_start) as usize * piece.char_size();
+            let mut bytes = vec![0u8; (cp_end - cp_start) as usize * piece.char_size()];
+            let read = ole.read_stream_range(&self.location, offset as u64, &mut bytes)?;
+            bytes.truncate(read);
+            parts.push(TextPart {
+                piece: index,
+                cp_start,
+                bytes,
+            });
+        }
+        Ok(parts)
+    }
+
+    /// Find the CP of the last paragraph or cell mark in `parts`.
+    fn last_paragraph_mark(pieces: &[PieceDescriptor], parts: &[TextPart]) -> Option<u32> {
+        parts.iter().rev().find_map(|part| {
+            let index = if pieces[part.piece].is_ansi {
+                part.bytes
+                    .iter()
+                    .rposition(|&byte| byte == 0x0D || byte == 0x07)
+            } else {
+                part.bytes
+                    .chunks_exact(2)
+                    .rposition(|unit| unit == [0x0D, 0x00] || unit == [0x07, 0x00])
+            };
+            index.map(|index| part.cp_start + index as u32)
+        })
+    }
+
+    /// Read the FKP pages of both bin tables that start before `fc_end`.
+    fn load_pages<R: Read + Seek>(&mut self, ole: &mut OleFile<R>, fc_end: u64) -> Result<()> {
+        for bin_table in [self.chp_pages.as_mut(), self.pap_pages.as_mut()]
+            .into_iter()
+            .flatten()
+        {
+            while let Some(&(fc, pn)) = bin_table.pages.get(bin_table.loaded) {
+                if u64::from(fc) >= fc_end {
+                    break;
+                }
+                bin_table.loaded += 1;
+                if self.pages.contains_key(&pn) {
+                    continue;
+                }
+
+                // Pages past the end of the stream are skipped
+                let mut page = vec![0u8; FKP_PAGE_SIZE];
+                let offset = u64::from(pn) * FKP_PAGE_SIZE as u64;
+                if ole.read_stream_range(&self.location, offset, &mut page)? == FKP_PAGE_SIZE {
+                    self.pages.insert(pn, page);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse the bin tables from the FKP pages read so far.
+    fn bin_tables(&self) -> (Option<ChpBinTable>, Option<PapBinTable>) {
+        let Some(piece_table) = self.piece_table.as_ref() else {
+            return (None, None);
+        };
+        let page = |pn| self.pages.get(&pn).map(Vec::as_slice);
+        let chp_bin_table = self.chp_pages.as_ref().and_then(|pages| {
+            ChpBinTable::parse_with_pages(&self.table_stream[pages.plcf.clone()], page, piece_table)
+        });
+        let pap_bin_table = self.pap_pages.as_ref().and_then(|pages| {
+            PapBinTable::parse_with_pages(&self.table_stream[pages.plcf.clone()], page, piece_table)
+        });
+        (chp_bin_table, pap_bin_table)
+    }
+}
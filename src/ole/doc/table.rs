@@ -2,6 +2,7 @@
 use super::package::Result;
 use super::paragraph::Paragraph;
 use super::parts::tap::{CellProperties, TableJustification, TableProperties};
+use crate::common::Length;
 use std::sync::Arc;
 
 /// A table in a Word document.
@@ -31,6 +32,12 @@ pub struct Table {
     rows: Arc<Vec<Row>>,
     /// Table-level properties (if available)
     properties: Option<TableProperties>,
+    /// Number of document paragraphs the table spans, including row marks
+    /// and nested tables
+    paragraph_count: usize,
+    /// Boundaries of the grid columns (twips), from the cell boundaries of
+    /// all rows
+    column_boundaries: Vec<i16>,
 }
 
 impl Table {
@@ -40,6 +47,8 @@ impl Table {
         Self {
             rows: Arc::new(rows),
             properties: None,
+            paragraph_count: 0,
+            column_boundaries: Vec::new(),
         }
     }
 
@@ -49,9 +58,59 @@ impl Table {
         Self {
             rows: Arc::new(rows),
             properties: Some(properties),
+            paragraph_count: 0,
+            column_boundaries: Vec::new(),
         }
     }
 
+    /// Create a new Table, taking the table properties from its first row.
+    ///
+    /// The grid columns are the union of the cell boundaries of all rows, and
+    /// each cell spans the grid columns between its boundaries.
+    pub(crate) fn from_rows(mut rows: Vec<Row>) -> Self {
+        let mut column_boundaries: Vec<i16> = rows
+            .iter()
+            .filter_map(Row::properties)
+            .flat_map(|tap| tap.cell_boundaries.iter().copied())
+            .collect();
+        column_boundaries.sort_unstable();
+        column_boundaries.dedup();
+
+        for row in &mut rows {
+            let Some(boundaries) = row.properties().map(|tap| tap.cell_boundaries.clone()) else {
+                continue;
+            };
+            let grid_index = |boundary: &i16| column_boundaries.binary_search(boundary).ok();
+            row.grid_before = boundaries.first().and_then(grid_index).unwrap_or(0);
+            for (cell, edges) in Arc::make_mut(&mut row.cells)
+                .iter_mut()
+                .zip(boundaries.windows(2))
+            {
+                if let (Some(start), Some(end)) = (grid_index(&edges[0]), grid_index(&edges[1])) {
+                    cell.grid_span = end.saturating_sub(start).max(1);
+                }
+            }
+        }
+
+        let mut table = match rows.first().and_then(|row| row.properties().cloned()) {
+            Some(properties) => Self::with_properties(rows, properties),
+            None => Self::new(rows),
+        };
+        table.column_boundaries = column_boundaries;
+        table
+    }
+
+    /// Set the number of document paragraphs the table spans.
+    pub(crate) fn with_paragraph_count(mut self, count: usize) -> Self {
+        self.paragraph_count = count;
+        self
+    }
+
+    /// Get the number of document paragraphs the table spans.
+    pub(crate) fn paragraph_count(&self) -> usize {
+        self.paragraph_count
+    }
+
     /// Get the number of rows in this table.
     pub fn row_count(&self) -> Result<usize> {
         Ok(self.rows.len())
@@ -88,6 +147,16 @@ impl Table {
         }
     }
 
+    /// Get the width of each grid column.
+    ///
+    /// Returns an empty list if the rows carry no cell boundaries.
+    pub fn column_widths(&self) -> Vec<Length> {
+        self.column_boundaries
+            .windows(2)
+            .map(|edges| Length::from_twips(i64::from(edges[1]) - i64::from(edges[0])))
+            .collect()
+    }
+
     /// Get the table properties.
     ///
     /// Returns the table-level formatting properties if available.
@@ -120,6 +189,8 @@ pub struct Row {
     cells: Arc<Vec<Cell>>,
     /// Row-level properties (if available)
     row_properties: Option<TableProperties>,
+    /// Number of grid columns before the first cell
+    grid_before: usize,
 }
 
 impl Row {
@@ -129,6 +200,7 @@ impl Row {
         Self {
             cells: Arc::new(cells),
             row_properties: None,
+            grid_before: 0,
         }
     }
 
@@ -138,6 +210,7 @@ impl Row {
         Self {
             cells: Arc::new(cells),
             row_properties: Some(properties),
+            grid_before: 0,
         }
     }
 
@@ -159,6 +232,11 @@ impl Row {
         self.row_properties.as_ref()
     }
 
+    /// Get the number of grid columns the row leaves out before its first cell.
+    pub fn grid_before(&self) -> usize {
+        self.grid_before
+    }
+
     /// Get the row height in twips (1/1440 inch).
     pub fn height(&self) -> Option<i16> {
         self.row_properties.as_ref().and_then(|p| p.row_height)
@@ -186,8 +264,12 @@ pub struct Cell {
     text: Arc<String>,
     /// Cell content (paragraphs) - shared via Arc for efficient cloning
     paragraphs: Arc<Vec<Paragraph>>,
+    /// Nested tables, each with the number of paragraphs preceding it
+    tables: Arc<Vec<(usize, Table)>>,
     /// Cell properties (if available)
     properties: Option<CellProperties>,
+    /// Number of grid columns the cell spans
+    grid_span: usize,
 }
 
 impl Cell {
@@ -198,7 +280,9 @@ impl Cell {
         Self {
             text: Arc::new(text),
             paragraphs: Arc::new(vec![para]),
+            tables: Arc::new(Vec::new()),
             properties: None,
+            grid_span: 1,
         }
     }
 
@@ -210,16 +294,53 @@ impl Cell {
     ) -> Self {
         let text = paragraphs
             .iter()
-            .filter_map(|p| p.text().ok())
-            .collect::<Vec<&str>>()
+            .map(paragraph_text)
+            .collect::<Vec<String>>()
             .join("\n");
         Self {
             text: Arc::new(text),
             paragraphs: Arc::new(paragraphs),
+            tables: Arc::new(Vec::new()),
             properties,
+            grid_span: 1,
         }
     }
 
+    /// Add the tables nested in this cell.
+    ///
+    /// Each table comes with the number of the cell's paragraphs preceding it.
+    /// The cell text then includes the text of the nested tables.
+    pub(crate) fn with_tables(mut self, tables: Vec<(usize, Table)>) -> Self {
+        self.tables = Arc::new(tables);
+        let mut parts = Vec::new();
+        for element in self.blocks() {
+            match element {
+                Block::Paragraph(para) => parts.push(paragraph_text(para)),
+                Block::Table(table) => {
+                    for row in table.rows.iter() {
+                        parts.extend(row.cells.iter().map(|cell| cell.text.to_string()));
+                    }
+                },
+            }
+        }
+        self.text = Arc::new(parts.join("\n"));
+        self
+    }
+
+    /// Iterate over the paragraphs and nested tables in document order.
+    fn blocks(&self) -> impl Iterator<Item = Block<'_>> {
+        let mut tables = self.tables.iter().peekable();
+        let mut blocks = Vec::with_capacity(self.paragraphs.len() + self.tables.len());
+        for (index, para) in self.paragraphs.iter().enumerate() {
+            while let Some((_, table)) = tables.next_if(|(before, _)| *before <= index) {
+                blocks.push(Block::Table(table));
+            }
+            blocks.push(Block::Paragraph(para));
+        }
+        blocks.extend(tables.map(|(_, table)| Block::Table(table)));
+        blocks.into_iter()
+    }
+
     /// Get the text content of this cell.
     ///
     /// Concatenates all text from all paragraphs in the cell.
@@ -227,13 +348,44 @@ impl Cell {
         Ok(&self.text)
     }
 
-    /// Get all paragraphs in this cell.
+    /// Get the number of grid columns this cell spans.
     ///
+    /// Cells keep their width in the TAP of their row, so the span is only
+    /// known once the cell is part of a table.
+    pub fn grid_span(&self) -> usize {
+        self.grid_span
+    }
+
+    /// Get the paragraphs directly in this cell.
+    ///
+    /// Paragraphs of nested tables are left out; see [`Cell::tables`].
     /// Returns a cloned vector. Cloning is relatively cheap due to Rc-based sharing.
     pub fn paragraphs(&self) -> Result<Vec<Paragraph>> {
         Ok((*self.paragraphs).clone())
     }
 
+    /// Get the tables nested directly in this cell.
+    pub fn tables(&self) -> Result<Vec<Table>> {
+        Ok(self.tables.iter().map(|(_, table)| table.clone()).collect())
+    }
+
+    /// Get the paragraphs and nested tables of this cell in document order.
+    pub fn elements(&self) -> Result<Vec<crate::document::DocumentElement>> {
+        use crate::document::DocumentElement;
+
+        Ok(self
+            .blocks()
+            .map(|block| match block {
+                Block::Paragraph(para) => DocumentElement::Paragraph(Box::new(
+                    crate::document::Paragraph::Doc(para.clone()),
+                )),
+                Block::Table(table) => {
+                    DocumentElement::Table(Box::new(crate::document::Table::Doc(table.clone())))
+                },
+            })
+            .collect())
+    }
+
     /// Get the cell properties.
     pub fn properties(&self) -> Option<&CellProperties> {
         self.properties.as_ref()
@@ -250,6 +402,25 @@ impl Cell {
     }
 }
 
+/// A paragraph or nested table of a cell.
+enum Block<'a> {
+    Paragraph(&'a Paragraph),
+    Table(&'a Table),
+}
+
+/// Get the text of a paragraph; extracted paragraphs keep it in their runs.
+fn paragraph_text(para: &Paragraph) -> String {
+    match para.text() {
+        Ok(text) if !text.is_empty() => text.to_string(),
+        _ => para
+            .runs()
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|run| run.text().ok())
+            .collect(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,38 @@
+/// Text box structures for Word documents
+use super::package::Result;
+use super::paragraph::Paragraph;
+
+/// A text box anchored in the main document of a Word document
+#[derive(Debug, Clone)]
+pub struct TextBox {
+    /// Anchor position of the shape in the main document
+    pub anchor_position: u32,
+    /// Shape identifier of the text box in the drawing
+    pub shape_id: u32,
+    /// Text content
+    pub text: String,
+    /// Paragraphs in this text box
+    pub paragraphs: Vec<Paragraph>,
+}
+
+impl TextBox {
+    /// Create a new text box
+    pub fn new(anchor_position: u32, shape_id: u32, text: String) -> Self {
+        Self {
+            anchor_position,
+            shape_id,
+            text,
+            paragraphs: Vec::new(),
+        }
+    }
+
+    /// Get the text content
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the paragraphs
+    pub fn paragraphs(&self) -> Result<&[Paragraph]> {
+        Ok(&self.paragraphs)
+    }
+}
@@ -40,7 +40,11 @@ pub use properties::{
 pub use record::EscherRecord;
 pub use shape::{EscherShape, EscherShapeType};
 pub use shape_factory::EscherShapeFactory;
-pub use text::extract_text_from_escher;
+pub use text::{
+    EscherTextOptions, extract_body_text_from_escher, extract_text_from_escher,
+    extract_text_from_escher_with_options, extract_text_from_escher_without_tables,
+    extract_title_from_escher, extract_title_from_escher_with_outline,
+};
 pub use types::EscherRecordType;
 pub use writer::{
     EscherProperty, EscherRecordHeader, EscherSpData, PropertyBuilder, ShapeBuilder, ShapeFlags,
@@ -277,6 +277,8 @@ pub enum EscherPropertyId {
     GroupName = 0x0380,
     GroupDescription = 0x0381,
     Hyperlink = 0x0382,
+    TableProperties = 0x039F,
+    TableRowProperties = 0x03A0,
     DiagramType = 0x0500,
     DiagramStyle = 0x0501,
     Unknown = 0xFFFF,
@@ -534,6 +536,8 @@ impl From<u16> for EscherPropertyId {
             0x0380 => Self::GroupName,
             0x0381 => Self::GroupDescription,
             0x0382 => Self::Hyperlink,
+            0x039F => Self::TableProperties,
+            0x03A0 => Self::TableRowProperties,
             0x0500 => Self::DiagramType,
             0x0501 => Self::DiagramStyle,
             _ => Self::Unknown,
@@ -970,7 +974,47 @@ impl ShapeAnchor {
         Some(Self::new(left, top, right, bottom))
     }
 
+    /// Parse a PowerPoint ClientAnchor record.
+    ///
+    /// The anchor is either a `SmallRectStruct` (four `i16`) or a `RectStruct`
+    /// (four `i32`), both stored in top, left, right, bottom order.
     pub fn from_client_anchor(anchor: &EscherRecord) -> Option<Self> {
-        Self::from_child_anchor(anchor)
+        let data = anchor.data;
+        let [top, left, right, bottom] = if data.len() >= 16 {
+            std::array::from_fn(|i| {
+                i32::from_le_bytes([
+                    data[i * 4],
+                    data[i * 4 + 1],
+                    data[i * 4 + 2],
+                    data[i * 4 + 3],
+                ])
+            })
+        } else if data.len() >= 8 {
+            std::array::from_fn(|i| i16::from_le_bytes([data[i * 2], data[i * 2 + 1]]) as i32)
+        } else {
+            return None;
+        };
+
+        Some(Self::new(left, top, right, bottom))
+    }
+
+    /// Map an anchor from a group's child coordinate space into the space
+    /// of the group's own anchor.
+    pub fn map_into(&self, from: &ShapeAnchor, to: &ShapeAnchor) -> Self {
+        let scale = |value: i32, from_start: i32, from_len: i32, to_start: i32, to_len: i32| {
+            if from_len == 0 {
+                return to_start;
+            }
+            let offset = (value - from_start) as i64 * to_len as i64 / from_len as i64;
+            to_start + offset as i32
+        };
+        let (fw, fh, tw, th) = (from.width(), from.height(), to.width(), to.height());
+
+        Self::new(
+            scale(self.left, from.left, fw, to.left, tw),
+            scale(self.top, from.top, fh, to.top, th),
+            scale(self.right, from.left, fw, to.left, tw),
+            scale(self.bottom, from.top, fh, to.top, th),
+        )
     }
 }
@@ -7,7 +7,7 @@
 //! - Enum-based shape type dispatch (no trait objects)
 
 use super::container::EscherContainer;
-use super::properties::{EscherProperties, ShapeAnchor};
+use super::properties::{EscherProperties, EscherPropertyId, ShapeAnchor};
 use super::types::EscherRecordType;
 
 /// Escher shape type enumeration.
@@ -25,6 +25,11 @@ pub enum EscherShapeType {
     Unknown,
 }
 
+/// `fFlipH` bit of the Sp record flags.
+const SP_FLAG_FLIP_H: u32 = 0x0040;
+/// `fFlipV` bit of the Sp record flags.
+const SP_FLAG_FLIP_V: u32 = 0x0080;
+
 /// Escher shape structure.
 ///
 /// # Performance
@@ -48,7 +53,7 @@ impl<'data> EscherShape<'data> {
     pub fn from_container(container: EscherContainer<'data>) -> Self {
         let shape_type = Self::detect_shape_type(&container);
         let shape_id = Self::extract_shape_id(&container);
-        let properties = EscherProperties::from_container(&container);
+        let properties = EscherProperties::from_container(&Self::own_container(&container));
         let anchor = Self::extract_anchor(&container);
 
         let text = if let Some(textbox) = container.find_child(EscherRecordType::ClientTextbox) {
@@ -118,6 +123,73 @@ impl<'data> EscherShape<'data> {
         self.anchor.as_ref()
     }
 
+    /// Replace the anchor, e.g. after mapping it out of a group's child space.
+    #[inline]
+    pub(crate) fn set_anchor(&mut self, anchor: ShapeAnchor) {
+        self.anchor = Some(anchor);
+    }
+
+    /// Get the clockwise rotation of the shape in degrees.
+    pub fn rotation(&self) -> f32 {
+        self.properties
+            .get_rotation_degrees(EscherPropertyId::Rotation)
+            .unwrap_or(0.0)
+    }
+
+    /// Check whether the shape is flipped horizontally.
+    #[inline]
+    pub fn flip_horizontal(&self) -> bool {
+        self.sp_flags() & SP_FLAG_FLIP_H != 0
+    }
+
+    /// Check whether the shape is flipped vertically.
+    #[inline]
+    pub fn flip_vertical(&self) -> bool {
+        self.sp_flags() & SP_FLAG_FLIP_V != 0
+    }
+
+    /// Move the anchors of a group's descendants into the coordinates of the
+    /// group's own anchor.
+    ///
+    /// Child anchors are relative to the group's child space, so call this on
+    /// top-level shapes once their anchors are in slide coordinates.
+    pub(crate) fn place_children(&mut self) {
+        let transform = self.child_space().zip(self.anchor);
+        for child in &mut self.children {
+            if let (Some((from, to)), Some(anchor)) = (transform, child.anchor) {
+                child.anchor = Some(anchor.map_into(&from, &to));
+            }
+            child.place_children();
+        }
+    }
+
+    /// Get the coordinate space of a group's children from its Spgr record.
+    pub fn child_space(&self) -> Option<ShapeAnchor> {
+        let own = Self::own_container(&self.container);
+        own.find_child(EscherRecordType::Spgr)
+            .and_then(|spgr| ShapeAnchor::from_child_anchor(&spgr))
+    }
+
+    fn sp_flags(&self) -> u32 {
+        Self::own_container(&self.container)
+            .find_child(EscherRecordType::Sp)
+            .filter(|sp| sp.data.len() >= 8)
+            .map(|sp| u32::from_le_bytes([sp.data[4], sp.data[5], sp.data[6], sp.data[7]]))
+            .unwrap_or(0)
+    }
+
+    /// Get the SpContainer holding the shape's own records.
+    ///
+    /// A group keeps them in the first SpContainer of its SpgrContainer.
+    fn own_container(container: &EscherContainer<'data>) -> EscherContainer<'data> {
+        if container.record().record_type == EscherRecordType::SpgrContainer
+            && let Some(sp_container) = container.find_child(EscherRecordType::SpContainer)
+        {
+            return EscherContainer::new(sp_container);
+        }
+        container.clone()
+    }
+
     pub fn can_contain_text(&self) -> bool {
         matches!(
             self.shape_type,
@@ -126,11 +198,35 @@ impl<'data> EscherShape<'data> {
     }
 
     pub fn text(&self) -> Option<String> {
-        if let Some(textbox) = self.container.find_child(EscherRecordType::ClientTextbox) {
-            super::text::extract_text_from_textbox(&textbox)
-        } else {
-            None
-        }
+        self.text_with_outline(&[])
+    }
+
+    /// Get the text of the shape, resolving an OutlineTextRefAtom against
+    /// the text blocks of the slide's outline.
+    pub fn text_with_outline(&self, outline_text: &[String]) -> Option<String> {
+        self.container
+            .find_child(EscherRecordType::ClientTextbox)
+            .and_then(|textbox| {
+                super::text::extract_text_from_textbox_with_outline(&textbox, outline_text)
+            })
+    }
+
+    /// Get the placement id of the shape's OEPlaceholderAtom (PPT), if the
+    /// shape is a placeholder.
+    pub fn placeholder_id(&self) -> Option<u8> {
+        self.container
+            .find_child(EscherRecordType::ClientData)
+            .and_then(|client_data| super::text::find_embedded_ppt_record(client_data.data, 3011))
+            .filter(|placeholder| placeholder.len() >= 8)
+            .map(|placeholder| placeholder[4])
+    }
+
+    /// Check if this is a group shape holding a table.
+    ///
+    /// PowerPoint stores tables as groups of cell text boxes, flagged by the
+    /// `tableProperties` property of the group's own shape record.
+    pub fn is_table(&self) -> bool {
+        self.shape_type == EscherShapeType::Group && is_table_group(&self.container)
     }
 
     #[inline]
@@ -227,7 +323,7 @@ impl<'data> EscherShape<'data> {
     }
 
     fn extract_shape_id(container: &EscherContainer<'data>) -> Option<u32> {
-        if let Some(sp) = container.find_child(EscherRecordType::Sp)
+        if let Some(sp) = Self::own_container(container).find_child(EscherRecordType::Sp)
             && sp.data.len() >= 4
         {
             let id = u32::from_le_bytes([sp.data[0], sp.data[1], sp.data[2], sp.data[3]]);
@@ -237,6 +333,8 @@ impl<'data> EscherShape<'data> {
     }
 
     fn extract_anchor(container: &EscherContainer<'data>) -> Option<ShapeAnchor> {
+        let container = &Self::own_container(container);
+
         if let Some(child_anchor) = container.find_child(EscherRecordType::ChildAnchor)
             && let Some(anchor) = ShapeAnchor::from_child_anchor(&child_anchor)
         {
@@ -252,3 +350,22 @@ impl<'data> EscherShape<'data> {
         None
     }
 }
+
+/// Check if an SpgrContainer holds a table.
+///
+/// The group's own properties live in its first SpContainer; bit 0 of
+/// `tableProperties` marks the group as a table.
+pub(crate) fn is_table_group(container: &EscherContainer<'_>) -> bool {
+    let Some(group_sp) = container.find_child(EscherRecordType::SpContainer) else {
+        return false;
+    };
+    let group_sp = EscherContainer::new(group_sp);
+    [EscherRecordType::TertiaryOpt, EscherRecordType::Opt]
+        .into_iter()
+        .filter_map(|record_type| group_sp.find_child(record_type))
+        .any(|opt| {
+            EscherProperties::from_opt_record(&opt)
+                .get_int(EscherPropertyId::TableProperties)
+                .is_some_and(|flags| flags & 1 != 0)
+        })
+}
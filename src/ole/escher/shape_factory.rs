@@ -7,6 +7,7 @@
 //! - Pattern matching for shape type detection
 
 use super::container::EscherContainer;
+use super::properties::ShapeAnchor;
 use super::record::Result;
 use super::shape::EscherShape;
 use super::types::EscherRecordType;
@@ -35,6 +36,56 @@ impl EscherShapeFactory {
         Ok(shapes)
     }
 
+    /// Extract the shapes of an Escher drawing as a tree.
+    ///
+    /// Unlike [`extract_shapes_from_drawing`](Self::extract_shapes_from_drawing),
+    /// groups are kept: each group is a single shape built from its
+    /// SpgrContainer, with its members in [`EscherShape::children`] at any
+    /// depth. The patriarch group of the drawing is unwrapped, so the result
+    /// holds the top-level shapes. All anchors are in drawing coordinates.
+    pub fn extract_shape_tree_from_drawing(data: &[u8]) -> Result<Vec<EscherShape<'_>>> {
+        let parser = super::parser::EscherParser::new(data);
+
+        let mut shapes = Vec::new();
+
+        if let Some(root_result) = parser.root_container() {
+            let root = root_result?;
+            Self::extract_shape_tree_from_container(&root, &mut shapes);
+        }
+
+        for shape in &mut shapes {
+            shape.place_children();
+        }
+
+        Ok(shapes)
+    }
+
+    /// Collect the top-level shapes of a container, unwrapping the patriarch.
+    fn extract_shape_tree_from_container<'data>(
+        container: &EscherContainer<'data>,
+        shapes: &mut Vec<EscherShape<'data>>,
+    ) {
+        for child in container.children().flatten() {
+            match child.record_type {
+                EscherRecordType::SpContainer => {
+                    let sp_container = EscherContainer::new(child);
+                    shapes.push(EscherShape::from_container(sp_container));
+                },
+                EscherRecordType::SpgrContainer => {
+                    // The drawing's outermost group is the patriarch; its
+                    // members are the top-level shapes
+                    let patriarch = EscherShape::from_container(EscherContainer::new(child));
+                    shapes.extend(patriarch.children);
+                },
+                _ if child.is_container() => {
+                    let child_container = EscherContainer::new(child);
+                    Self::extract_shape_tree_from_container(&child_container, shapes);
+                },
+                _ => {},
+            }
+        }
+    }
+
     /// Recursively extract shapes from a container.
     ///
     /// # Implementation Notes
@@ -55,7 +106,7 @@ impl EscherShapeFactory {
                 },
                 EscherRecordType::SpgrContainer => {
                     let group_container = EscherContainer::new(child);
-                    Self::extract_shapes_from_spgr_container(&group_container, shapes);
+                    Self::extract_shapes_from_spgr_container(&group_container, shapes, None);
                 },
                 _ if child.is_container() => {
                     let child_container = EscherContainer::new(child);
@@ -71,11 +122,26 @@ impl EscherShapeFactory {
     /// Based on Apache POI's HSLFGroupShape.getShapes():
     /// - The first SpContainer in SpgrContainer is the group shape itself
     /// - Remaining SpContainer children are the actual child shapes
+    ///
+    /// Table groups are kept whole so their cells can be laid out as a grid.
+    ///
+    /// Child anchors are relative to the group's coordinate space; `transform`
+    /// maps them onto the enclosing group's anchor so every shape ends up in
+    /// slide coordinates.
     fn extract_shapes_from_spgr_container<'data>(
         container: &EscherContainer<'data>,
         shapes: &mut Vec<EscherShape<'data>>,
+        transform: Option<GroupTransform>,
     ) {
+        if super::shape::is_table_group(container) {
+            let mut table = EscherShape::from_container(container.clone());
+            GroupTransform::place(transform, &mut table);
+            shapes.push(table);
+            return;
+        }
+
         let mut is_first = true;
+        let mut child_transform = transform;
 
         for child in container.children().flatten() {
             match child.record_type {
@@ -84,16 +150,27 @@ impl EscherShapeFactory {
 
                     if is_first {
                         is_first = false;
-                        let group_shape = EscherShape::from_container(sp_container);
+                        let mut group_shape = EscherShape::from_container(sp_container);
+                        GroupTransform::place(transform, &mut group_shape);
+                        if let (Some(from), Some(to)) =
+                            (group_shape.child_space(), group_shape.anchor())
+                        {
+                            child_transform = Some(GroupTransform { from, to: *to });
+                        }
                         shapes.push(group_shape);
                     } else {
-                        let child_shape = EscherShape::from_container(sp_container);
+                        let mut child_shape = EscherShape::from_container(sp_container);
+                        GroupTransform::place(child_transform, &mut child_shape);
                         shapes.push(child_shape);
                     }
                 },
                 EscherRecordType::SpgrContainer => {
                     let nested_group = EscherContainer::new(child);
-                    Self::extract_shapes_from_spgr_container(&nested_group, shapes);
+                    Self::extract_shapes_from_spgr_container(
+                        &nested_group,
+                        shapes,
+                        child_transform,
+                    );
                 },
                 _ if child.is_container() => {
                     let child_container = EscherContainer::new(child);
@@ -143,3 +220,22 @@ impl EscherShapeFactory {
         count
     }
 }
+
+/// Mapping from a group's child coordinate space to its anchor.
+#[derive(Debug, Clone, Copy)]
+struct GroupTransform {
+    from: ShapeAnchor,
+    to: ShapeAnchor,
+}
+
+impl GroupTransform {
+    /// Move the anchor of a shape inside a group into slide coordinates.
+    fn place(transform: Option<Self>, shape: &mut EscherShape<'_>) {
+        if let Some(transform) = transform
+            && let Some(anchor) = shape.anchor()
+        {
+            let mapped = anchor.map_into(&transform.from, &transform.to);
+            shape.set_anchor(mapped);
+        }
+    }
+}
@@ -4,6 +4,7 @@
 //!
 //! - Extracts text from ClientTextbox records
 //! - Supports PPT-specific text parsing (TextCharsAtom, TextBytesAtom)
+//! - Resolves PPT OutlineTextRefAtom records against the slide's outline text
 //! - Zero-copy where possible
 
 use super::container::EscherContainer;
@@ -19,18 +20,157 @@ use super::types::EscherRecordType;
 /// - Pre-allocated string buffers
 /// - Reuses buffer across extractions
 pub fn extract_text_from_escher(escher_data: &[u8]) -> Result<String> {
+    extract_text_from_escher_with_options(escher_data, &EscherTextOptions::default())
+}
+
+/// Extract text from an Escher record hierarchy, leaving out table cells.
+///
+/// Tables are groups of cell text boxes; their content is better read as a
+/// table (see [`EscherShape::is_table`](super::EscherShape::is_table)).
+pub fn extract_text_from_escher_without_tables(escher_data: &[u8]) -> Result<String> {
+    extract_text_from_escher_with_options(
+        escher_data,
+        &EscherTextOptions {
+            skip_tables: true,
+            ..EscherTextOptions::default()
+        },
+    )
+}
+
+/// Extract text from an Escher record hierarchy, leaving out the slide title.
+///
+/// See [`extract_title_from_escher`] for how the title is identified.
+pub fn extract_body_text_from_escher(escher_data: &[u8]) -> Result<String> {
+    extract_text_from_escher_with_options(
+        escher_data,
+        &EscherTextOptions {
+            skip_titles: true,
+            ..EscherTextOptions::default()
+        },
+    )
+}
+
+/// Extract the slide title from an Escher record hierarchy.
+///
+/// The title is the text of the first shape whose TextHeaderAtom has the
+/// title (0) or center title (6) text type. Shapes without a TextHeaderAtom
+/// are matched by the title placeholder of their OEPlaceholderAtom.
+pub fn extract_title_from_escher(escher_data: &[u8]) -> Result<Option<String>> {
+    extract_title_from_escher_with_outline(escher_data, &[])
+}
+
+/// Extract the slide title, resolving text kept in the slide's outline.
+///
+/// Like [`extract_title_from_escher`], but title placeholders whose text is
+/// an OutlineTextRefAtom take it from `outline_text`.
+pub fn extract_title_from_escher_with_outline(
+    escher_data: &[u8],
+    outline_text: &[String],
+) -> Result<Option<String>> {
+    let parser = super::parser::EscherParser::new(escher_data);
+
+    match parser.root_container() {
+        Some(root_result) => Ok(find_title_in_container(&root_result?, outline_text)),
+        None => Ok(None),
+    }
+}
+
+/// Options for extracting text from an Escher record hierarchy.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EscherTextOptions<'a> {
+    /// Leave out table cells
+    pub skip_tables: bool,
+    /// Leave out the slide title
+    pub skip_titles: bool,
+    /// Text blocks of the slide's outline (SlideListWithText), in order.
+    ///
+    /// PowerPoint keeps placeholder text there and stores only an
+    /// OutlineTextRefAtom with the block index in the shape.
+    pub outline_text: &'a [String],
+}
+
+/// Extract text from an Escher record hierarchy with the given options.
+///
+/// Text boxes are found at any depth, including inside nested groups.
+pub fn extract_text_from_escher_with_options(
+    escher_data: &[u8],
+    options: &EscherTextOptions,
+) -> Result<String> {
     let mut result = String::with_capacity(1024);
 
     let parser = super::parser::EscherParser::new(escher_data);
 
     if let Some(root_result) = parser.root_container() {
         let root = root_result?;
-        extract_text_from_container_into(&root, &mut result);
+        extract_text_from_container_into(&root, &mut result, options);
     }
 
     Ok(result)
 }
 
+fn find_title_in_container(container: &EscherContainer, outline_text: &[String]) -> Option<String> {
+    for child in container.children().flatten() {
+        if child.record_type == EscherRecordType::SpContainer {
+            let sp_container = EscherContainer::new(child);
+            if is_title_shape(&sp_container) {
+                return sp_container
+                    .find_child(EscherRecordType::ClientTextbox)
+                    .and_then(|textbox| {
+                        extract_text_from_textbox_with_outline(&textbox, outline_text)
+                    });
+            }
+        } else if child.is_container() {
+            let child_container = EscherContainer::new(child);
+            if let Some(title) = find_title_in_container(&child_container, outline_text) {
+                return Some(title);
+            }
+        }
+    }
+    None
+}
+
+/// Check whether an SpContainer holds the slide title.
+fn is_title_shape(container: &EscherContainer) -> bool {
+    // TextHeaderAtom text types: 0 = title, 6 = center title
+    if let Some(textbox) = container.find_child(EscherRecordType::ClientTextbox)
+        && let Some(header) = find_embedded_ppt_record(textbox.data, 3999)
+        && header.len() >= 4
+    {
+        let text_type = u32::from_le_bytes([header[0], header[1], header[2], header[3]]);
+        return matches!(text_type, 0 | 6);
+    }
+
+    // OEPlaceholderAtom placeholder ids: 13 = title, 15 = centered title,
+    // 17 = vertical text title
+    container
+        .find_child(EscherRecordType::ClientData)
+        .and_then(|client_data| find_embedded_ppt_record(client_data.data, 3011))
+        .is_some_and(|placeholder| placeholder.len() >= 8 && matches!(placeholder[4], 13 | 15 | 17))
+}
+
+/// Find the data of the first top-level PPT record of the given type.
+pub(crate) fn find_embedded_ppt_record(data: &[u8], record_type: u16) -> Option<&[u8]> {
+    let mut offset = 0;
+
+    while offset + 8 <= data.len() {
+        let record_type_raw = u16::from_le_bytes([data[offset + 2], data[offset + 3]]);
+        let data_length = u32::from_le_bytes([
+            data[offset + 4],
+            data[offset + 5],
+            data[offset + 6],
+            data[offset + 7],
+        ]) as usize;
+        let end = (offset + 8).saturating_add(data_length).min(data.len());
+
+        if record_type_raw == record_type {
+            return Some(&data[offset + 8..end]);
+        }
+        offset = end.max(offset + 8);
+    }
+
+    None
+}
+
 /// Recursively extract text from a container and its children into a pre-allocated buffer.
 ///
 /// # Performance
@@ -38,23 +178,35 @@ pub fn extract_text_from_escher(escher_data: &[u8]) -> Result<String> {
 /// - Single String buffer reused throughout recursion
 /// - No intermediate Vec allocations
 /// - Minimal string copies
-fn extract_text_from_container_into(container: &EscherContainer, result: &mut String) {
+fn extract_text_from_container_into(
+    container: &EscherContainer,
+    result: &mut String,
+    options: &EscherTextOptions,
+) {
     for child in container.children().flatten() {
         match child.record_type {
             EscherRecordType::ClientTextbox => {
                 let before_len = result.len();
-                extract_text_from_textbox_into(&child, result);
+                extract_text_from_textbox_into(&child, options.outline_text, result);
                 if result.len() > before_len && !result.is_empty() && !result.ends_with('\n') {
                     result.push('\n');
                 }
             },
             EscherRecordType::SpContainer => {
                 let sp_container = EscherContainer::new(child);
-                extract_text_from_container_into(&sp_container, result);
+                if options.skip_titles && is_title_shape(&sp_container) {
+                    continue;
+                }
+                extract_text_from_container_into(&sp_container, result, options);
             },
             _ if child.is_container() => {
+                let is_group = child.record_type == EscherRecordType::SpgrContainer;
                 let child_container = EscherContainer::new(child);
-                extract_text_from_container_into(&child_container, result);
+                if options.skip_tables && is_group && super::shape::is_table_group(&child_container)
+                {
+                    continue;
+                }
+                extract_text_from_container_into(&child_container, result, options);
             },
             _ => {},
         }
@@ -65,8 +217,17 @@ fn extract_text_from_container_into(container: &EscherContainer, result: &mut St
 ///
 /// Returns Option<String> for backwards compatibility.
 pub fn extract_text_from_textbox(textbox: &EscherRecord) -> Option<String> {
+    extract_text_from_textbox_with_outline(textbox, &[])
+}
+
+/// Extract text from a ClientTextbox record, resolving OutlineTextRefAtom
+/// records against the slide's outline text blocks.
+pub fn extract_text_from_textbox_with_outline(
+    textbox: &EscherRecord,
+    outline_text: &[String],
+) -> Option<String> {
     let mut result = String::with_capacity(256);
-    extract_text_from_textbox_into(textbox, &mut result);
+    extract_text_from_textbox_into(textbox, outline_text, &mut result);
     if result.is_empty() {
         None
     } else {
@@ -83,14 +244,18 @@ pub fn extract_text_from_textbox(textbox: &EscherRecord) -> Option<String> {
 /// - Zero-copy: directly parses text from byte slices
 /// - Writes directly to output buffer
 /// - 5-10x faster than full record parsing
-fn extract_text_from_textbox_into(textbox: &EscherRecord, result: &mut String) {
+fn extract_text_from_textbox_into(
+    textbox: &EscherRecord,
+    outline_text: &[String],
+    result: &mut String,
+) {
     if textbox.data.is_empty() {
         return;
     }
 
     let initial_len = result.len();
 
-    extract_text_from_embedded_ppt_records(textbox.data, result);
+    extract_text_from_embedded_ppt_records(textbox.data, outline_text, result);
 
     if result.len() > initial_len {
         let trimmed_end = result.trim_end();
@@ -107,7 +272,11 @@ fn extract_text_from_textbox_into(textbox: &EscherRecord, result: &mut String) {
 /// - Zero allocations for non-text records
 /// - Direct text parsing from byte slices
 /// - Minimal branching with match expressions
-fn extract_text_from_embedded_ppt_records(data: &[u8], result: &mut String) {
+fn extract_text_from_embedded_ppt_records(
+    data: &[u8],
+    outline_text: &[String],
+    result: &mut String,
+) {
     use zerocopy::{
         FromBytes,
         byteorder::{LittleEndian, U16, U32},
@@ -135,6 +304,24 @@ fn extract_text_from_embedded_ppt_records(data: &[u8], result: &mut String) {
         let record_data = &data[offset + 8..offset + 8 + actual_size];
 
         match record_type_raw {
+            // OutlineTextRefAtom: index of the text block in the slide's outline
+            3998 if actual_size >= 4 => {
+                let index = u32::from_le_bytes([
+                    record_data[0],
+                    record_data[1],
+                    record_data[2],
+                    record_data[3],
+                ]) as usize;
+                if let Some(text) = outline_text.get(index) {
+                    let trimmed = text.trim();
+                    if !trimmed.is_empty() {
+                        if !result.is_empty() && !result.ends_with('\n') {
+                            result.push('\n');
+                        }
+                        result.push_str(trimmed);
+                    }
+                }
+            },
             4000 => {
                 if let Ok(text) = parse_text_chars_atom(record_data) {
                     let trimmed = text.trim();
@@ -169,7 +356,7 @@ fn extract_text_from_embedded_ppt_records(data: &[u8], result: &mut String) {
                 }
             },
             _ if is_ppt_container_record(record_type_raw) && actual_size > 0 => {
-                extract_text_from_embedded_ppt_records(record_data, result);
+                extract_text_from_embedded_ppt_records(record_data, outline_text, result);
             },
             _ => {},
         }
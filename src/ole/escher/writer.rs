@@ -290,6 +290,9 @@ impl ShapeBuilder {
 }
 
 /// Write a ClientAnchor record.
+///
+/// The coordinates are stored as a `RectStruct` in top, left, right, bottom
+/// order.
 pub fn write_client_anchor<W: Write>(
     writer: &mut W,
     left: i32,
@@ -298,8 +301,8 @@ pub fn write_client_anchor<W: Write>(
     bottom: i32,
 ) -> io::Result<()> {
     write_record_header(writer, 0x00, 0, 0xF010, 16)?;
-    writer.write_all(&left.to_le_bytes())?;
     writer.write_all(&top.to_le_bytes())?;
+    writer.write_all(&left.to_le_bytes())?;
     writer.write_all(&right.to_le_bytes())?;
     writer.write_all(&bottom.to_le_bytes())?;
     Ok(())
@@ -1,7 +1,8 @@
 use super::consts::*;
 use super::file::{OleError, OleFile};
+use crate::common::CustomPropertyValue;
 use chrono::{DateTime, Duration, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Read, Seek};
 use zerocopy::{FromBytes, I16, I32, LE, U16, U32};
 
@@ -35,6 +36,9 @@ pub struct OleMetadata {
     pub category: Option<String>,
     pub manager: Option<String>,
     pub company: Option<String>,
+
+    // User-defined properties (second DocumentSummaryInformation section)
+    pub custom_properties: BTreeMap<String, CustomPropertyValue>,
 }
 
 /// Property value types
@@ -47,8 +51,12 @@ pub enum PropertyValue {
     Bool(bool),
     Lpstr(Vec<u8>), // Raw bytes for ANSI strings (need codepage to decode)
     Lpwstr(String), // Already decoded UTF-16
+    I8(i64),
+    R8(f64),
     Filetime(u64),
     Blob(Vec<u8>),
+    /// Property ID to name mapping of a section (property ID 0)
+    Dictionary(HashMap<u32, String>),
     Empty,
 }
 
@@ -67,11 +75,24 @@ impl<R: Read + Seek> OleFile<R> {
             extract_summary_info(&mut metadata, &props);
         }
 
-        // Try to parse DocumentSummaryInformation stream
+        // Try to parse DocumentSummaryInformation stream. Its optional second
+        // section holds the user-defined properties, although some writers put
+        // the dictionary straight into the first section.
         if let Ok(data) = self.open_stream(&["\u{0005}DocumentSummaryInformation"])
-            && let Ok(props) = parse_property_stream(&data)
+            && let Ok(offsets) = section_offsets(&data)
         {
-            extract_document_summary_info(&mut metadata, &props);
+            if let Ok(props) = parse_section(&data, offsets[0]) {
+                if props.contains_key(&0) {
+                    extract_user_defined_properties(&mut metadata, &props);
+                } else {
+                    extract_document_summary_info(&mut metadata, &props);
+                }
+            }
+            if let Some(&offset) = offsets.get(1)
+                && let Ok(props) = parse_section(&data, offset)
+            {
+                extract_user_defined_properties(&mut metadata, &props);
+            }
         }
 
         Ok(metadata)
@@ -87,9 +108,7 @@ fn filetime_to_date(filetime: u64) -> Option<DateTime<Utc>> {
     // Number of 100-nanosecond intervals between 1601-01-01 and 1970-01-01
     const EPOCH_DIFF: i64 = 116_444_736_000_000_000;
     let doc_epoch = i64::try_from(filetime).ok()?;
-    Some(DateTime::from_timestamp_nanos(
-        (doc_epoch - EPOCH_DIFF) * 100,
-    ))
+    DateTime::from_timestamp_micros((doc_epoch - EPOCH_DIFF) / 10)
 }
 
 /// Convert a FILETIME property value to Rust duration
@@ -97,26 +116,58 @@ fn filetime_to_date(filetime: u64) -> Option<DateTime<Utc>> {
 /// It is like [filetime_to_date], but the result is a duration instead of a date.
 #[inline]
 fn filetime_to_duration(filetime: u64) -> Option<Duration> {
-    let nanos = filetime * 100;
+    let nanos = filetime.checked_mul(100)?;
     Some(Duration::nanoseconds(i64::try_from(nanos).ok()?))
 }
 
-/// Parse a property stream and return properties as a HashMap
+/// Parse a property stream and return the properties of its first section
 ///
 /// Property streams contain metadata in a structured format according
 /// to [MS-OLEPS] specification.
 fn parse_property_stream(data: &[u8]) -> Result<HashMap<u32, PropertyValue>, OleError> {
+    let offsets = section_offsets(data)?;
+    parse_section(data, offsets[0])
+}
+
+/// Read the offsets of the property set sections in a property stream
+///
+/// The stream header (28 bytes) is followed by one FMTID (16 bytes) and
+/// offset (4 bytes) pair per section. At least one section is always present.
+fn section_offsets(data: &[u8]) -> Result<Vec<usize>, OleError> {
     if data.len() < 48 {
         return Err(OleError::InvalidFormat(
             "Property stream too short".to_string(),
         ));
     }
 
-    // Skip header (28 bytes) and format ID (20 bytes)
-    let section_offset = U32::<LE>::read_from_bytes(&data[44..48])
+    let num_sections = U32::<LE>::read_from_bytes(&data[24..28])
         .map(|v| v.get() as usize)
-        .unwrap_or(0);
+        .unwrap_or(1)
+        .clamp(1, 2);
 
+    let mut offsets = Vec::with_capacity(num_sections);
+    for i in 0..num_sections {
+        let pos = 44 + i * 20;
+        if pos + 4 > data.len() {
+            break;
+        }
+        let offset = U32::<LE>::read_from_bytes(&data[pos..pos + 4])
+            .map(|v| v.get() as usize)
+            .unwrap_or(0);
+        offsets.push(offset);
+    }
+
+    Ok(offsets)
+}
+
+/// Parse the properties of one section as a HashMap
+///
+/// The dictionary (property ID 0) has no type tag; it is returned as
+/// [`PropertyValue::Dictionary`] with names decoded using the section codepage.
+fn parse_section(
+    data: &[u8],
+    section_offset: usize,
+) -> Result<HashMap<u32, PropertyValue>, OleError> {
     if section_offset + 8 > data.len() {
         return Err(OleError::InvalidFormat(
             "Invalid section offset".to_string(),
@@ -133,6 +184,7 @@ fn parse_property_stream(data: &[u8]) -> Result<HashMap<u32, PropertyValue>, Ole
 
     // Create a HashMap with the estimated number of properties
     let mut properties = HashMap::with_capacity(num_props as usize);
+    let mut dictionary_offset = None;
 
     // Parse each property
     for i in 0..num_props {
@@ -156,6 +208,11 @@ fn parse_property_stream(data: &[u8]) -> Result<HashMap<u32, PropertyValue>, Ole
             continue;
         }
 
+        if prop_id == 0 {
+            dictionary_offset = Some(value_offset);
+            continue;
+        }
+
         // Property type
         let prop_type = U16::<LE>::read_from_bytes(&data[value_offset..value_offset + 2])
             .map(|v| v.get())
@@ -167,9 +224,79 @@ fn parse_property_stream(data: &[u8]) -> Result<HashMap<u32, PropertyValue>, Ole
         }
     }
 
+    if let Some(offset) = dictionary_offset {
+        let codepage = match properties.get(&1) {
+            Some(PropertyValue::I2(v)) => Some(*v as u16 as u32),
+            _ => None,
+        };
+        properties.insert(
+            0,
+            PropertyValue::Dictionary(parse_dictionary(data, offset, codepage)),
+        );
+    }
+
     Ok(properties)
 }
 
+/// Parse a property set dictionary mapping property IDs to names
+///
+/// With the Unicode codepage (1200) names are UTF-16LE, their length counts
+/// characters and each entry is padded to 4 bytes; otherwise the length counts
+/// bytes in the section codepage.
+fn parse_dictionary(data: &[u8], offset: usize, codepage: Option<u32>) -> HashMap<u32, String> {
+    const CP_WINUNICODE: u32 = 1200;
+
+    let mut names = HashMap::new();
+    let Some(count) = data
+        .get(offset..offset + 4)
+        .and_then(|b| U32::<LE>::read_from_bytes(b).ok())
+        .map(|v| v.get().min(1000))
+    else {
+        return names;
+    };
+
+    let unicode = codepage == Some(CP_WINUNICODE);
+    let mut pos = offset + 4;
+    for _ in 0..count {
+        let Some(header) = data.get(pos..pos + 8) else {
+            break;
+        };
+        let prop_id = U32::<LE>::read_from_bytes(&header[0..4])
+            .map(|v| v.get())
+            .unwrap_or(0);
+        let length = U32::<LE>::read_from_bytes(&header[4..8])
+            .map(|v| v.get() as usize)
+            .unwrap_or(0);
+        pos += 8;
+
+        let byte_len = if unicode { length * 2 } else { length };
+        let Some(raw) = data.get(pos..pos + byte_len) else {
+            break;
+        };
+        let name = if unicode {
+            let units: Vec<u16> = raw
+                .chunks_exact(2)
+                .map(|c| u16::from_le_bytes([c[0], c[1]]))
+                .take_while(|&u| u != 0)
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        } else {
+            let end = raw.iter().position(|&b| b == 0).unwrap_or(raw.len());
+            crate::common::encoding::decode_bytes(&raw[..end], codepage)
+        };
+        if let Some(name) = name {
+            names.insert(prop_id, name);
+        }
+
+        pos += byte_len;
+        if unicode {
+            pos = pos.next_multiple_of(4);
+        }
+    }
+
+    names
+}
+
 /// Parse a single property value based on its type
 fn parse_property_value(
     data: &[u8],
@@ -265,6 +392,24 @@ fn parse_property_value(
             let s = String::from_utf16_lossy(&utf16_chars);
             Ok(PropertyValue::Lpwstr(s))
         },
+        VT_I8 | VT_UI8 => {
+            // 64-bit integer
+            if offset + 8 > data.len() {
+                return Err(OleError::InvalidFormat("Buffer overflow".to_string()));
+            }
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&data[offset..offset + 8]);
+            Ok(PropertyValue::I8(i64::from_le_bytes(bytes)))
+        },
+        VT_R8 => {
+            // 64-bit floating point
+            if offset + 8 > data.len() {
+                return Err(OleError::InvalidFormat("Buffer overflow".to_string()));
+            }
+            let mut bytes = [0u8; 8];
+            bytes.copy_from_slice(&data[offset..offset + 8]);
+            Ok(PropertyValue::R8(f64::from_le_bytes(bytes)))
+        },
         VT_FILETIME => {
             // 64-bit file time
             if offset + 8 > data.len() {
@@ -321,7 +466,7 @@ fn extract_summary_info(metadata: &mut OleMetadata, props: &HashMap<u32, Propert
     // Property IDs for SummaryInformation (start at 1)
     // 1: CODEPAGE
     let codepage = if let Some(PropertyValue::I2(v)) = props.get(&1) {
-        let cp = Some(*v as u32);
+        let cp = Some(*v as u16 as u32);
         metadata.codepage = cp;
         cp
     } else {
@@ -443,6 +588,44 @@ fn extract_document_summary_info(metadata: &mut OleMetadata, props: &HashMap<u32
     }
 }
 
+/// Extract user-defined properties into metadata
+///
+/// The user-defined section names its properties through the dictionary;
+/// properties without a name or with an unsupported type are skipped.
+fn extract_user_defined_properties(
+    metadata: &mut OleMetadata,
+    props: &HashMap<u32, PropertyValue>,
+) {
+    let Some(PropertyValue::Dictionary(names)) = props.get(&0) else {
+        return;
+    };
+    let codepage = match props.get(&1) {
+        Some(PropertyValue::I2(v)) => Some(*v as u16 as u32),
+        _ => metadata.codepage,
+    };
+
+    for (id, name) in names {
+        let value = match props.get(id) {
+            Some(PropertyValue::I2(v)) => CustomPropertyValue::Integer(i64::from(*v)),
+            Some(PropertyValue::I4(v)) => CustomPropertyValue::Integer(i64::from(*v)),
+            Some(PropertyValue::UI2(v)) => CustomPropertyValue::Integer(i64::from(*v)),
+            Some(PropertyValue::UI4(v)) => CustomPropertyValue::Integer(i64::from(*v)),
+            Some(PropertyValue::I8(v)) => CustomPropertyValue::Integer(*v),
+            Some(PropertyValue::R8(v)) => CustomPropertyValue::Float(*v),
+            Some(PropertyValue::Bool(v)) => CustomPropertyValue::Bool(*v),
+            Some(PropertyValue::Filetime(v)) => match filetime_to_date(*v) {
+                Some(date) => CustomPropertyValue::DateTime(date),
+                None => continue,
+            },
+            Some(value @ (PropertyValue::Lpstr(_) | PropertyValue::Lpwstr(_))) => {
+                CustomPropertyValue::String(extract_string(value, codepage).unwrap_or_default())
+            },
+            _ => continue,
+        };
+        metadata.custom_properties.insert(name.clone(), value);
+    }
+}
+
 /// Extract string from property value with proper encoding
 fn extract_string(value: &PropertyValue, codepage: Option<u32>) -> Option<String> {
     match value {
@@ -463,3 +646,131 @@ fn extract_string(value: &PropertyValue, codepage: Option<u32>) -> Option<String
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a property stream with an empty first section and a user-defined
+    /// second section holding `props` (already encoded as type + value).
+    fn user_defined_stream(dictionary: &[(u32, &str)], props: &[(u32, Vec<u8>)]) -> Vec<u8> {
+        // Section: codepage 1200, dictionary, then the properties
+        let mut entries: Vec<(u32, Vec<u8>)> = vec![(1, {
+            let mut v = (VT_I2 as u32).to_le_bytes().to_vec();
+            v.extend_from_slice(&1200u16.to_le_bytes());
+            v.extend_from_slice(&[0, 0]);
+            v
+        })];
+        let mut dict = (dictionary.len() as u32).to_le_bytes().to_vec();
+        for (id, name) in dictionary {
+            let units: Vec<u16> = name.encode_utf16().chain(std::iter::once(0)).collect();
+            dict.extend_from_slice(&id.to_le_bytes());
+            dict.extend_from_slice(&(units.len() as u32).to_le_bytes());
+            for u in units {
+                dict.extend_from_slice(&u.to_le_bytes());
+            }
+            while !dict.len().is_multiple_of(4) {
+                dict.push(0);
+            }
+        }
+        entries.push((0, dict));
+        entries.extend(props.iter().cloned());
+
+        let mut section = Vec::new();
+        let header_len = 8 + entries.len() * 8;
+        let mut body = Vec::new();
+        let mut index = Vec::new();
+        for (id, value) in &entries {
+            index.extend_from_slice(&id.to_le_bytes());
+            index.extend_from_slice(&((header_len + body.len()) as u32).to_le_bytes());
+            body.extend_from_slice(value);
+        }
+        section.extend_from_slice(&((header_len + body.len()) as u32).to_le_bytes());
+        section.extend_from_slice(&(entries.len() as u32).to_le_bytes());
+        section.extend_from_slice(&index);
+        section.extend_from_slice(&body);
+
+        let empty_section = [8u32.to_le_bytes(), 0u32.to_le_bytes()].concat();
+        let first_offset = 28 + 2 * 20;
+        let second_offset = first_offset + empty_section.len();
+
+        let mut data = vec![0xFE, 0xFF, 0, 0, 0, 0, 0, 0];
+        data.extend_from_slice(&[0u8; 16]);
+        data.extend_from_slice(&2u32.to_le_bytes());
+        data.extend_from_slice(&[0u8; 16]);
+        data.extend_from_slice(&(first_offset as u32).to_le_bytes());
+        data.extend_from_slice(&[0u8; 16]);
+        data.extend_from_slice(&(second_offset as u32).to_le_bytes());
+        data.extend_from_slice(&empty_section);
+        data.extend_from_slice(&section);
+        data
+    }
+
+    fn typed(vt: u16, value: &[u8]) -> Vec<u8> {
+        let mut v = (vt as u32).to_le_bytes().to_vec();
+        v.extend_from_slice(value);
+        while !v.len().is_multiple_of(4) {
+            v.push(0);
+        }
+        v
+    }
+
+    #[test]
+    fn user_defined_properties_are_typed() {
+        let client: Vec<u8> = {
+            let units: Vec<u16> = "Acme".encode_utf16().chain(std::iter::once(0)).collect();
+            let mut v = (units.len() as u32).to_le_bytes().to_vec();
+            for u in units {
+                v.extend_from_slice(&u.to_le_bytes());
+            }
+            v
+        };
+        // 2024-01-02T03:04:05Z as FILETIME
+        let filetime: u64 = (1_704_164_645 * 10_000_000) + 116_444_736_000_000_000;
+
+        let data = user_defined_stream(
+            &[
+                (2, "Client"),
+                (3, "Budget"),
+                (4, "Approved"),
+                (5, "Due"),
+                (6, "Count"),
+            ],
+            &[
+                (2, typed(VT_LPWSTR, &client)),
+                (3, typed(VT_R8, &1234.5f64.to_le_bytes())),
+                (4, typed(VT_BOOL, &0xFFFFu16.to_le_bytes())),
+                (5, typed(VT_FILETIME, &filetime.to_le_bytes())),
+                (6, typed(VT_I4, &42i32.to_le_bytes())),
+            ],
+        );
+
+        let offsets = section_offsets(&data).unwrap();
+        assert_eq!(offsets.len(), 2);
+        let props = parse_section(&data, offsets[1]).unwrap();
+
+        let mut metadata = OleMetadata::default();
+        extract_user_defined_properties(&mut metadata, &props);
+
+        let custom = &metadata.custom_properties;
+        assert_eq!(
+            custom.get("Client"),
+            Some(&CustomPropertyValue::String("Acme".to_string()))
+        );
+        assert_eq!(
+            custom.get("Budget"),
+            Some(&CustomPropertyValue::Float(1234.5))
+        );
+        assert_eq!(
+            custom.get("Approved"),
+            Some(&CustomPropertyValue::Bool(true))
+        );
+        assert_eq!(custom.get("Count"), Some(&CustomPropertyValue::Integer(42)));
+        assert_eq!(
+            custom.get("Due"),
+            Some(&CustomPropertyValue::DateTime(
+                DateTime::from_timestamp(1_704_164_645, 0).unwrap()
+            ))
+        );
+    }
+}
@@ -10,6 +10,12 @@ pub mod writer;
 /// Metadata extraction from OLE property streams
 mod metadata;
 
+/// Embedded OLE objects (compound file storages with CompObj, Ole10Native, ...)
+pub mod object;
+
+/// VBA projects (macros) stored in compound files
+pub mod vba;
+
 /// MTEF extractor for OLE documents (internal use only)
 #[cfg(feature = "formula")]
 mod mtef_extractor;
@@ -57,7 +63,11 @@ pub mod ppt;
 pub mod xls;
 
 // Re-export public types for convenient access
-pub use file::{DirectoryEntry, OleError, OleFile, is_ole_file};
+pub(crate) use file::OleSource;
+#[cfg(test)]
+pub(crate) use file::{ByteMutator, CountingReader, ReadLog};
+pub use file::{DirectoryEntry, OleError, OleFile, StreamLocation, is_ole_file};
 pub use metadata::{OleMetadata, PropertyValue};
+pub use vba::{VbaModule, VbaModuleKind, VbaProject, VbaReference, VbaReferenceKind};
 pub use writer::OleWriter;
 pub use xls::{XlsError, XlsWorkbook};
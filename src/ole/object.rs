@@ -0,0 +1,397 @@
+//! Embedded OLE object storages.
+//!
+//! An OLE object embedded in a document is a compound file storage: the
+//! `\x01CompObj` stream names the object's class, and the object's native
+//! data sits next to it. OLE1-style objects (including the "Package" object
+//! that wraps an arbitrary file) keep their data in `\x01Ole10Native`, while
+//! embedded Office Open XML files use a `Package` stream. Word (.doc) stores
+//! these storages under `ObjectPool`; Office Open XML files store each one as
+//! a standalone compound file part (`embeddings/oleObject1.bin`).
+//!
+//! Presentation (preview) pictures are read from `\x03EPRINT` (EMF),
+//! `\x03META` (WMF behind a METAFILEPICT header) or `\x02OlePres000`.
+
+use super::file::{OleError, OleFile, format_clsid};
+use super::writer::OleWriter;
+use std::io::{Cursor, Read, Seek};
+
+/// Entry type of a storage in the compound file directory
+const STGTY_STORAGE: u8 = 1;
+
+/// Streams holding the native data of an object, in order of preference.
+///
+/// `package_stream` is where LibreOffice keeps the ODF package of its own
+/// embedded objects.
+const PAYLOAD_STREAMS: [&str; 3] = ["Package", "CONTENTS", "package_stream"];
+
+/// Standard clipboard format of a Windows metafile picture
+const CF_METAFILEPICT: u32 = 3;
+
+/// Standard clipboard format of an enhanced metafile
+const CF_ENHMETAFILE: u32 = 14;
+
+/// An embedded OLE object read from its compound file storage.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectStorage {
+    /// CLSID of the object's class, formatted as `XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX`
+    pub clsid: Option<String>,
+    /// Programmatic identifier from the `\x01CompObj` stream, e.g. `Excel.Sheet.8`
+    pub prog_id: Option<String>,
+    /// Original file name of a "Package" object
+    pub file_name: Option<String>,
+    /// Native data of the object, e.g. the file wrapped by a "Package" object
+    pub payload: Option<Vec<u8>>,
+    /// Presentation picture of the object (EMF or WMF)
+    pub preview: Option<Vec<u8>>,
+}
+
+impl ObjectStorage {
+    /// Parse an object stored as a standalone compound file.
+    pub fn parse(data: &[u8]) -> Result<Self, OleError> {
+        let mut ole = OleFile::open(Cursor::new(data))?;
+        Ok(Self::from_storage(&mut ole, &[]))
+    }
+
+    /// Read the object in the storage at `path` (the root for an empty path).
+    pub fn from_storage<R: Read + Seek>(ole: &mut OleFile<R>, path: &[&str]) -> Self {
+        let mut stream_path = path.to_vec();
+        let mut read = |name: &'static str| {
+            stream_path.push(name);
+            let data = ole.open_stream(&stream_path).ok();
+            stream_path.pop();
+            data
+        };
+
+        let comp_obj = read("\x01CompObj").map(|data| parse_comp_obj(&data));
+        let ole10_native = read("\x01Ole10Native");
+        let payload_stream = PAYLOAD_STREAMS.into_iter().find_map(&mut read);
+        let preview = read("\x03EPRINT")
+            .or_else(|| read("\x03META").map(|data| strip_metafile_pict(&data)))
+            .or_else(|| read("\x02OlePres000").and_then(|data| parse_ole_presentation(&data)));
+
+        let (comp_obj_clsid, prog_id) = comp_obj.unwrap_or_default();
+        let clsid = ole.get_clsid(path).map(str::to_string).or(comp_obj_clsid);
+
+        let mut object = Self {
+            clsid,
+            prog_id,
+            preview,
+            ..Default::default()
+        };
+        if let Some(native) = ole10_native {
+            let (file_name, payload) = if object.prog_id.as_deref() == Some("Package") {
+                parse_package(&native)
+            } else {
+                (None, None)
+            };
+            object.file_name = file_name;
+            object.payload = payload.or_else(|| native_data(&native));
+        } else {
+            object.payload = payload_stream;
+        }
+        object
+    }
+}
+
+/// Copy the storage at `path` into a standalone compound file.
+///
+/// The copy carries the storage's CLSID on its root entry, matching the
+/// object parts Word writes to `embeddings/*.bin`.
+pub fn extract_storage<R: Read + Seek>(
+    ole: &mut OleFile<R>,
+    path: &[&str],
+) -> Result<Vec<u8>, OleError> {
+    let mut writer = OleWriter::new();
+    if let Some(clsid) = ole.get_clsid(path).and_then(parse_clsid) {
+        writer.set_root_clsid(clsid);
+    }
+    let mut source = path.iter().map(|name| name.to_string()).collect();
+    copy_storage(ole, &mut source, &mut Vec::new(), &mut writer)?;
+
+    let mut out = Cursor::new(Vec::new());
+    writer.write_to(&mut out)?;
+    Ok(out.into_inner())
+}
+
+/// Recursively copy the storage at `source` to `target` in the writer
+fn copy_storage<R: Read + Seek>(
+    ole: &mut OleFile<R>,
+    source: &mut Vec<String>,
+    target: &mut Vec<String>,
+    writer: &mut OleWriter,
+) -> Result<(), OleError> {
+    let dir: Vec<&str> = source.iter().map(String::as_str).collect();
+    let entries: Vec<(String, u8)> = ole
+        .list_directory_entries(&dir)?
+        .iter()
+        .map(|entry| (entry.name.clone(), entry.entry_type))
+        .collect();
+
+    for (name, entry_type) in entries {
+        source.push(name.clone());
+        target.push(name);
+        let target_path: Vec<&str> = target.iter().map(String::as_str).collect();
+        if entry_type == STGTY_STORAGE {
+            writer.create_storage(&target_path)?;
+            copy_storage(ole, source, target, writer)?;
+        } else {
+            let source_path: Vec<&str> = source.iter().map(String::as_str).collect();
+            let data = ole.open_stream(&source_path)?;
+            writer.create_stream(&target_path, &data)?;
+        }
+        source.pop();
+        target.pop();
+    }
+    Ok(())
+}
+
+/// Parse a formatted CLSID back into its 16 bytes
+fn parse_clsid(clsid: &str) -> Option<[u8; 16]> {
+    let hex: String = clsid.chars().filter(|c| *c != '-').collect();
+    if hex.len() != 32 {
+        return None;
+    }
+    let mut bytes = [0u8; 16];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(hex.get(i * 2..i * 2 + 2)?, 16).ok()?;
+    }
+    // The first three fields are stored little-endian
+    bytes[0..4].reverse();
+    bytes[4..6].reverse();
+    bytes[6..8].reverse();
+    Some(bytes)
+}
+
+/// Little-endian reader over a byte slice that yields `None` past the end
+struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn bytes(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.data.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.bytes(2).map(|b| u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.bytes(4)
+            .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    /// Read a null-terminated ANSI string
+    fn c_string(&mut self) -> Option<String> {
+        let len = self.data.get(self.pos..)?.iter().position(|&b| b == 0)?;
+        let text = decode_ansi(self.bytes(len)?);
+        self.pos += 1;
+        Some(text)
+    }
+
+    /// Read an ANSI string prefixed with its length (including the terminator)
+    fn length_prefixed_string(&mut self) -> Option<String> {
+        let len = self.u32()? as usize;
+        let bytes = self.bytes(len)?;
+        Some(decode_ansi(
+            bytes.split(|&b| b == 0).next().unwrap_or_default(),
+        ))
+    }
+
+    /// Skip a ClipboardFormatOrAnsiString, returning the standard format if any
+    fn clipboard_format(&mut self) -> Option<Option<u32>> {
+        match self.u32()? {
+            0 => Some(None),
+            0xFFFF_FFFF | 0xFFFF_FFFE => self.u32().map(Some),
+            len => self.bytes(len as usize).map(|_| None),
+        }
+    }
+}
+
+fn decode_ansi(bytes: &[u8]) -> String {
+    encoding_rs::WINDOWS_1252.decode(bytes).0.into_owned()
+}
+
+/// Get the CLSID and ProgID from a `\x01CompObj` stream
+fn parse_comp_obj(data: &[u8]) -> (Option<String>, Option<String>) {
+    let mut reader = ByteReader::new(data);
+    // Reserved1, Version and the -1 marker precede the CLSID
+    let clsid = reader
+        .bytes(12)
+        .and_then(|_| reader.bytes(16))
+        .map(format_clsid)
+        .filter(|clsid| !clsid.is_empty());
+
+    // AnsiUserType and AnsiClipboardFormat precede the ProgID
+    let prog_id = reader
+        .length_prefixed_string()
+        .and_then(|_| reader.clipboard_format())
+        .and_then(|_| reader.length_prefixed_string())
+        .filter(|prog_id| !prog_id.is_empty());
+    (clsid, prog_id)
+}
+
+/// Get the native data of an `\x01Ole10Native` stream
+fn native_data(data: &[u8]) -> Option<Vec<u8>> {
+    let mut reader = ByteReader::new(data);
+    let size = reader.u32()? as usize;
+    let available = data.len() - 4;
+    reader.bytes(size.min(available)).map(<[u8]>::to_vec)
+}
+
+/// Get the file name and contents wrapped by a "Package" object
+fn parse_package(data: &[u8]) -> (Option<String>, Option<Vec<u8>>) {
+    let mut reader = ByteReader::new(data);
+    let mut parse = || {
+        reader.u32()?; // native data size
+        reader.u16()?; // type
+        let label = reader.c_string()?;
+        let source_path = reader.c_string()?;
+        reader.u32()?; // reserved, then the embedded file marker
+        let temp_path_len = reader.u32()? as usize;
+        reader.bytes(temp_path_len)?;
+        let size = reader.u32()? as usize;
+        let contents = reader.bytes(size)?.to_vec();
+
+        // Fall back to the source path when the label is empty
+        let file_name = if label.is_empty() {
+            source_path
+                .rsplit(['\\', '/'])
+                .next()
+                .unwrap_or_default()
+                .to_string()
+        } else {
+            label
+        };
+        Some(((!file_name.is_empty()).then_some(file_name), contents))
+    };
+
+    match parse() {
+        Some((file_name, contents)) => (file_name, Some(contents)),
+        None => (None, None),
+    }
+}
+
+/// Strip the METAFILEPICT header (mm, xExt, yExt, hMF) from a `\x03META` stream
+fn strip_metafile_pict(data: &[u8]) -> Vec<u8> {
+    data.get(8..).unwrap_or_default().to_vec()
+}
+
+/// Get the metafile of an `\x02OlePres000` presentation stream
+fn parse_ole_presentation(data: &[u8]) -> Option<Vec<u8>> {
+    let mut reader = ByteReader::new(data);
+    let format = reader.clipboard_format()?;
+    let target_device_size = reader.u32()? as usize;
+    reader.bytes(target_device_size.saturating_sub(4))?;
+    // Aspect, Lindex, Advf, Reserved1, Width and Height
+    reader.bytes(24)?;
+
+    match format {
+        Some(CF_METAFILEPICT) => {
+            let size = reader.u32()? as usize;
+            reader.bytes(size).map(<[u8]>::to_vec)
+        },
+        Some(CF_ENHMETAFILE) => {
+            // Locate the EMF header by its signature, which sits at offset 40
+            let size = reader.u32()? as usize;
+            let data = reader.bytes(size)?;
+            let emf_start = data
+                .windows(4)
+                .position(|w| w == b" EMF")?
+                .checked_sub(40)?;
+            Some(data[emf_start..].to_vec())
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_object_pool_storage() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/ole/doc/FloatingPictures.doc");
+        let mut ole = OleFile::open(std::fs::File::open(path).unwrap()).unwrap();
+
+        let data = extract_storage(&mut ole, &["ObjectPool", "_1207591883"]).unwrap();
+        let object = ObjectStorage::parse(&data).unwrap();
+        assert_eq!(
+            object.clsid.as_deref(),
+            Some("00020820-0000-0000-C000-000000000046")
+        );
+        assert_eq!(object.prog_id.as_deref(), Some("Excel.Sheet.8"));
+        // The workbook is the storage itself rather than a wrapped file
+        assert!(object.payload.is_none());
+        // \x03EPRINT holds an EMF
+        assert_eq!(object.preview.unwrap().get(40..44), Some(&b" EMF"[..]));
+
+        let mut copy = OleFile::open(Cursor::new(data)).unwrap();
+        assert!(copy.open_stream(&["Workbook"]).is_ok());
+    }
+
+    #[test]
+    fn test_parse_clsid_round_trip() {
+        let bytes = parse_clsid("00020820-0000-0000-C000-000000000046").unwrap();
+        assert_eq!(
+            bytes,
+            [
+                0x20, 0x08, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, 0x46
+            ]
+        );
+        assert!(parse_clsid("not-a-clsid").is_none());
+    }
+
+    #[test]
+    fn test_parse_comp_obj() {
+        let mut data = vec![
+            0x01, 0x00, 0xFE, 0xFF, 0x03, 0x0A, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+        ];
+        data.extend_from_slice(&[
+            0x20, 0x08, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0xC0, 0x00, 0x00, 0x00, 0x00, 0x00,
+            0x00, 0x46,
+        ]);
+        for text in [
+            "Microsoft Office Excel Worksheet\0",
+            "Biff8\0",
+            "Excel.Sheet.8\0",
+        ] {
+            data.extend_from_slice(&(text.len() as u32).to_le_bytes());
+            data.extend_from_slice(text.as_bytes());
+        }
+        let (clsid, prog_id) = parse_comp_obj(&data);
+        assert_eq!(
+            clsid.as_deref(),
+            Some("00020820-0000-0000-C000-000000000046")
+        );
+        assert_eq!(prog_id.as_deref(), Some("Excel.Sheet.8"));
+    }
+
+    #[test]
+    fn test_parse_package() {
+        let contents = b"hello";
+        let mut data = Vec::new();
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&2u16.to_le_bytes());
+        data.extend_from_slice(b"notes.txt\0C:\\docs\\notes.txt\0");
+        data.extend_from_slice(&[0x00, 0x00, 0x03, 0x00]);
+        data.extend_from_slice(&4u32.to_le_bytes());
+        data.extend_from_slice(b"C:\\\0");
+        data.extend_from_slice(&(contents.len() as u32).to_le_bytes());
+        data.extend_from_slice(contents);
+
+        let (file_name, payload) = parse_package(&data);
+        assert_eq!(file_name.as_deref(), Some("notes.txt"));
+        assert_eq!(payload.as_deref(), Some(&contents[..]));
+        assert_eq!(parse_package(&data[..20]), (None, None));
+    }
+}
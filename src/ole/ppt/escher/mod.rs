@@ -7,8 +7,13 @@
 pub use crate::ole::escher::{
     EscherArrayProperty, EscherContainer, EscherParser, EscherProperties, EscherPropertyId,
     EscherPropertyValue, EscherRecord, EscherRecordType, EscherShape, EscherShapeFactory,
-    EscherShapeType, ShapeAnchor, extract_text_from_escher,
+    EscherShapeType, EscherTextOptions, ShapeAnchor, extract_body_text_from_escher,
+    extract_text_from_escher, extract_text_from_escher_with_options,
+    extract_text_from_escher_without_tables, extract_title_from_escher,
+    extract_title_from_escher_with_outline,
 };
 
 // Re-export text extraction for backwards compatibility
-pub use crate::ole::escher::text::extract_text_from_textbox;
+pub use crate::ole::escher::text::{
+    extract_text_from_textbox, extract_text_from_textbox_with_outline,
+};
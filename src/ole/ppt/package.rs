@@ -175,6 +175,25 @@ impl<R: Read + Seek> Package<R> {
         Presentation::from_ole(&mut self.ole)
     }
 
+    /// Check that the presentation can be read, without parsing it.
+    ///
+    /// Locating the PowerPoint Document stream walks its sector chain, so
+    /// that a damaged file is rejected when it is opened rather than on
+    /// first use.
+    pub(crate) fn validate(&self) -> Result<()> {
+        let location = self.ole.stream_location(&["PowerPoint Document"]);
+        if location.is_err()
+            && self
+                .ole
+                .stream_location(&["PP97_DUALSTORAGE", "PowerPoint Document"])
+                .is_ok()
+        {
+            return Ok(());
+        }
+        location?;
+        Ok(())
+    }
+
     /// Get the underlying OLE file.
     ///
     /// This provides access to lower-level OLE operations and streams.
@@ -5,6 +5,7 @@
 use crate::ole::consts::PptRecordType;
 use crate::ole::ppt::package::{PptError, Result};
 use crate::ole::ppt::records::PptRecord;
+use std::collections::HashMap;
 
 /// Parser for PPT binary format that extracts document structure and content.
 pub struct PptRecordParser {
@@ -159,6 +160,47 @@ impl PptRecordParser {
             .filter(move |record| record.record_type == record_type)
     }
 
+    /// Get the current Document record.
+    ///
+    /// Incremental saves append records, so the last Document is current.
+    pub fn document(&self) -> Option<&PptRecord> {
+        self.records
+            .iter()
+            .rev()
+            .find(|record| record.record_type == PptRecordType::Document)
+    }
+
+    /// Get the outline text blocks of every slide, keyed by slide persist ID.
+    ///
+    /// PowerPoint keeps placeholder text in the slides' SlideListWithText of
+    /// the Document record rather than in the shapes; see
+    /// [`SlideAtomsSet::text_blocks`](crate::ole::ppt::records::SlideAtomsSet::text_blocks).
+    pub fn slide_outline_text(&self) -> HashMap<u32, Vec<String>> {
+        self.document()
+            .map(Self::document_outline_text)
+            .unwrap_or_default()
+    }
+
+    /// Get the outline text blocks of every slide listed in a Document record.
+    pub(crate) fn document_outline_text(document: &PptRecord) -> HashMap<u32, Vec<String>> {
+        let mut outline_text = HashMap::new();
+
+        // Instance 0 lists the slides; 1 and 2 list masters and notes
+        for slide_list in document
+            .extract_slide_list_with_texts()
+            .into_iter()
+            .filter(|slide_list| slide_list.get_instance() == 0)
+        {
+            for atoms_set in slide_list.group_into_slide_atoms_sets() {
+                if let Some(persist_id) = atoms_set.get_slide_id() {
+                    outline_text.insert(persist_id, atoms_set.text_blocks());
+                }
+            }
+        }
+
+        outline_text
+    }
+
     /// Extract all text content from the document.
     pub fn extract_all_text(&self) -> Result<String> {
         let mut text_parts = Vec::new();
@@ -1,13 +1,20 @@
 use super::super::OleFile;
 /// High-performance Presentation API with zero-copy slide parsing.
+use super::escher::EscherTextOptions;
 use super::package::{PptError, Result};
 use super::parsers::PptRecordParser;
 use super::persist::PersistMapping;
+use super::shapes::shape_enum::TableShape;
 use super::slide::{Slide, SlideFactory};
+use crate::common::Length;
+use crate::common::unit::ppt_master_i64_to_emu_i32;
+use crate::common::walk::{TextEvent, visit, visit_lines};
 #[cfg(feature = "imgconv")]
 use crate::images::{BlipStore, ExtractedImage, ImageExtractor};
 use crate::ole::consts::PptRecordType;
+use std::collections::HashMap;
 use std::io::{Read, Seek};
+use std::ops::ControlFlow;
 
 /// A PowerPoint presentation (.ppt) with high-performance zero-copy parsing.
 ///
@@ -35,11 +42,12 @@ use std::io::{Read, Seek};
 pub struct Presentation {
     /// The main document stream data (owned for lifetime management)
     powerpoint_document: Vec<u8>,
-    /// Parsed record structure (reserved for future advanced parsing)
-    #[allow(dead_code)]
+    /// Parsed record structure
     pub(crate) parser: PptRecordParser,
     /// Persist ID to offset mapping
     pub(crate) persist_mapping: PersistMapping,
+    /// Outline text blocks of each slide, keyed by slide persist ID
+    slide_outline_text: HashMap<u32, Vec<String>>,
     /// Pictures stream data (for image extraction)
     #[cfg(feature = "imgconv")]
     pictures_data: Option<Vec<u8>>,
@@ -63,6 +71,9 @@ impl Presentation {
         let all_records_ref = parser.find_records_ref();
         let persist_mapping = PersistMapping::build_from_records_ref(&all_records_ref);
 
+        // Placeholder text lives in the Document's SlideListWithText
+        let slide_outline_text = parser.slide_outline_text();
+
         // Try to read Pictures stream for image extraction
         #[cfg(feature = "imgconv")]
         let (pictures_data, blip_store) = if let Ok(pictures) = ole.open_stream(&["Pictures"]) {
@@ -79,6 +90,7 @@ impl Presentation {
             powerpoint_document,
             parser,
             persist_mapping,
+            slide_outline_text,
             #[cfg(feature = "imgconv")]
             pictures_data,
             #[cfg(feature = "imgconv")]
@@ -117,11 +129,44 @@ impl Presentation {
             .slides()
             .enumerate()
             .map(|(idx, slide_result)| {
-                slide_result.map(|slide_data| Slide::from_slide_data(slide_data, idx + 1))
+                slide_result.map(|slide_data| {
+                    let outline_text = self.outline_text(slide_data.persist_id);
+                    Slide::from_slide_data(slide_data, idx + 1).with_outline_text(outline_text)
+                })
             })
             .collect()
     }
 
+    /// Report the text of each slide to `visitor`, one paragraph per line,
+    /// stopping as soon as it returns [`ControlFlow::Break`].
+    ///
+    /// Slides are decoded one at a time, so no slide after the point where
+    /// the visitor stops is decoded.
+    pub fn walk_text(
+        &self,
+        visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        let factory = SlideFactory::new(&self.powerpoint_document, &self.persist_mapping);
+        for (idx, slide_data) in factory.slides().enumerate() {
+            let slide_data = slide_data?;
+            let outline_text = self.outline_text(slide_data.persist_id);
+            let slide = Slide::from_slide_data(slide_data, idx + 1).with_outline_text(outline_text);
+            visit!(visitor, TextEvent::SlideStart(idx));
+            if visit_lines(slide.text()?, visitor).is_break() {
+                return Ok(ControlFlow::Break(()));
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Get the outline text blocks of the slide with the given persist ID.
+    fn outline_text(&self, persist_id: u32) -> &[String] {
+        self.slide_outline_text
+            .get(&persist_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     /// Get the number of slides (actual Slide records only).
     #[inline]
     pub fn slide_count(&self) -> usize {
@@ -129,6 +174,20 @@ impl Presentation {
         factory.slide_ids().len()
     }
 
+    /// Get the slide size as (width, height).
+    ///
+    /// The size is read from the DocumentAtom, which stores it in master
+    /// units. Returns `None` when the document has no DocumentAtom.
+    pub fn slide_size(&self) -> Option<(Length, Length)> {
+        let info = self.parser.document()?.extract_document_info()?;
+        if info.slide_width == 0 || info.slide_height == 0 {
+            return None;
+        }
+        let length =
+            |master: u32| Length::from_emus(ppt_master_i64_to_emu_i32(master.into()).into());
+        Some((length(info.slide_width), length(info.slide_height)))
+    }
+
     /// Extract all text from the presentation.
     ///
     /// # Performance
@@ -186,10 +245,15 @@ impl Presentation {
             }
 
             // Extract text from Escher/PPDrawing using the optimized path
+            let options = EscherTextOptions {
+                outline_text: self.outline_text(slide_data.persist_id),
+                ..EscherTextOptions::default()
+            };
             if let Some(ppdrawing) = slide_data
                 .record
                 .find_child(crate::ole::consts::PptRecordType::PPDrawing)
-                && let Ok(escher_text) = super::escher::extract_text_from_escher(&ppdrawing.data)
+                && let Ok(escher_text) =
+                    super::escher::extract_text_from_escher_with_options(&ppdrawing.data, &options)
             {
                 let trimmed = escher_text.trim();
                 if !trimmed.is_empty() {
@@ -206,6 +270,72 @@ impl Presentation {
         Ok(results)
     }
 
+    /// Fast content extraction that keeps tables apart from the body text.
+    ///
+    /// Like [`Self::extract_text_fast`], but table cells are left out of the
+    /// slide text and returned as tables instead. Only table groups are
+    /// turned into shapes; other shapes are not parsed.
+    ///
+    /// # Returns
+    ///
+    /// Vector of (slide_number, text, tables) tuples for each slide
+    pub(crate) fn extract_content_fast(&self) -> Result<Vec<(usize, String, Vec<TableShape>)>> {
+        let factory = SlideFactory::new(&self.powerpoint_document, &self.persist_mapping);
+
+        let mut results = Vec::with_capacity(factory.slide_ids().len());
+
+        for (idx, slide_result) in factory.slides().enumerate() {
+            let slide_data = slide_result?;
+
+            let mut text = String::with_capacity(512);
+            let mut tables = Vec::new();
+
+            if let Ok(record_text) = slide_data.record.extract_text() {
+                let trimmed = record_text.trim();
+                if !trimmed.is_empty() {
+                    text.push_str(trimmed);
+                }
+            }
+
+            if let Some(ppdrawing) = slide_data
+                .record
+                .find_child(crate::ole::consts::PptRecordType::PPDrawing)
+            {
+                let options = EscherTextOptions {
+                    skip_tables: true,
+                    outline_text: self.outline_text(slide_data.persist_id),
+                    ..EscherTextOptions::default()
+                };
+                if let Ok(escher_text) =
+                    super::escher::extract_text_from_escher_with_options(&ppdrawing.data, &options)
+                {
+                    let trimmed = escher_text.trim();
+                    if !trimmed.is_empty() {
+                        if !text.is_empty() {
+                            text.push('\n');
+                        }
+                        text.push_str(trimmed);
+                    }
+                }
+
+                if let Ok(shapes) =
+                    super::escher::EscherShapeFactory::extract_shapes_from_drawing(&ppdrawing.data)
+                {
+                    tables.extend(
+                        shapes
+                            .iter()
+                            .filter(|shape| shape.is_table())
+                            .map(TableShape::from_escher),
+                    );
+                }
+            }
+
+            results.push((idx + 1, text, tables));
+        }
+
+        Ok(results)
+    }
+
     /// Extract all images from the presentation
     ///
     /// This extracts all embedded images from the Pictures stream.
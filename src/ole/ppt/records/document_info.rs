@@ -5,9 +5,9 @@
 /// Information extracted from a Document record.
 #[derive(Debug, Clone, Default)]
 pub struct DocumentInfo {
-    /// Slide width in EMUs (English Metric Units)
+    /// Slide width in master units (576 per inch)
     pub slide_width: u32,
-    /// Slide height in EMUs
+    /// Slide height in master units
     pub slide_height: u32,
     /// Number of slides in the presentation
     pub slide_count: usize,
@@ -3,6 +3,7 @@
 //! Based on POI's SlideListWithText.SlideAtomsSet inner class.
 
 use super::record::PptRecord;
+use crate::ole::consts::PptRecordType;
 use crate::ole::ppt::package::Result;
 
 /// A set of records associated with a single slide's text.
@@ -38,4 +39,29 @@ impl<'a> SlideAtomsSet<'a> {
     pub fn get_slide_id(&self) -> Option<u32> {
         self.slide_persist_atom.get_slide_id()
     }
+
+    /// Split the text of this set into its text blocks.
+    ///
+    /// Each TextHeaderAtom starts a block holding the text of the following
+    /// TextCharsAtom or TextBytesAtom. Shapes refer to a block by its index
+    /// with an OutlineTextRefAtom.
+    pub fn text_blocks(&self) -> Vec<String> {
+        let mut blocks: Vec<String> = Vec::new();
+
+        for record in &self.slide_records {
+            match record.record_type {
+                PptRecordType::TextHeaderAtom => blocks.push(String::new()),
+                PptRecordType::TextCharsAtom | PptRecordType::TextBytesAtom => {
+                    if let Some(block) = blocks.last_mut()
+                        && let Ok(text) = record.extract_text()
+                    {
+                        block.push_str(&text);
+                    }
+                },
+                _ => {},
+            }
+        }
+
+        blocks
+    }
 }
@@ -288,6 +288,11 @@ impl<'a> AutoShape<'a> {
         self.adjustments = adjustments;
     }
 
+    /// Set the text shown in the shape.
+    pub fn set_text(&mut self, text: String) {
+        self.container.set_text(text);
+    }
+
     /// Check if this is a basic geometric shape (rectangle, oval, etc.).
     pub fn is_basic_shape(&self) -> bool {
         matches!(
@@ -329,12 +334,11 @@ where
     }
 
     fn text(&self) -> super::super::package::Result<String> {
-        // Auto shapes may contain text, but it's optional
-        Ok(String::new())
+        Shape::text(&self.container)
     }
 
     fn has_text(&self) -> bool {
-        false // Auto shapes typically don't have inherent text
+        self.container.has_text()
     }
 
     fn clone_box(&self) -> Box<dyn Shape> {
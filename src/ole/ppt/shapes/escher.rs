@@ -619,8 +619,8 @@ impl<'a> EscherRecord<'a> {
             // Parse rotation if available (2 bytes)
             if transform.data.len() >= 18 {
                 props.rotation = U16::<LittleEndian>::read_from_bytes(&transform.data[16..18])
-                    .map(|v| v.get())
-                    .unwrap_or(0);
+                    .map(|v| v.get() as f64)
+                    .unwrap_or(0.0);
             }
         }
 
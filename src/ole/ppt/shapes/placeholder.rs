@@ -245,7 +245,7 @@ impl<'a> Placeholder<'a> {
 
     /// Map POI placeholder IDs to our placeholder types.
     /// This follows the mapping used in POI's Placeholder enum.
-    fn map_poi_placeholder_id_to_type(
+    pub(crate) fn map_poi_placeholder_id_to_type(
         poi_id: u16,
     ) -> super::super::package::Result<PlaceholderType> {
         // Based on POI's Placeholder enum mapping
@@ -317,6 +317,11 @@ impl<'a> Placeholder<'a> {
         self.index = Some(index);
     }
 
+    /// Set the text of the placeholder.
+    pub fn set_text(&mut self, text: String) {
+        self.container.set_text(text);
+    }
+
     /// Check if this is a title placeholder.
     pub fn is_title(&self) -> bool {
         matches!(
@@ -379,12 +384,11 @@ where
     }
 
     fn text(&self) -> super::super::package::Result<String> {
-        // Placeholders typically don't have their own text - they hold content
-        Ok(String::new())
+        Shape::text(&self.container)
     }
 
     fn has_text(&self) -> bool {
-        false // Placeholders don't have inherent text content
+        self.container.has_text()
     }
 
     fn clone_box(&self) -> Box<dyn Shape> {
@@ -141,8 +141,12 @@ pub struct ShapeProperties {
     pub width: i32,
     /// Height in EMUs
     pub height: i32,
-    /// Rotation angle (0-360 degrees)
-    pub rotation: u16,
+    /// Clockwise rotation angle in degrees
+    pub rotation: f64,
+    /// Is the shape flipped horizontally?
+    pub flip_horizontal: bool,
+    /// Is the shape flipped vertically?
+    pub flip_vertical: bool,
     /// Fill color (RGB)
     pub fill_color: Option<u32>,
     /// Line color (RGB)
@@ -164,7 +168,9 @@ impl Default for ShapeProperties {
             y: 0,
             width: 0,
             height: 0,
-            rotation: 0,
+            rotation: 0.0,
+            flip_horizontal: false,
+            flip_vertical: false,
             fill_color: None,
             line_color: None,
             line_width: None,
@@ -3,8 +3,10 @@
 //! Idiomatic Rust implementation using enum variants instead of trait objects.
 
 use super::picture::PictureShape;
-use super::shape::{Shape, ShapeType};
+use super::shape::{Shape, ShapeProperties, ShapeType};
 use super::{AutoShape, Placeholder, TextBox};
+use crate::common::Rect;
+use crate::ole::escher::{EscherShape, EscherShapeType, ShapeAnchor};
 use crate::ole::ppt::package::Result;
 
 /// Represents any shape on a slide using an enum for zero-cost abstraction.
@@ -29,7 +31,7 @@ pub enum ShapeEnum<'a> {
     Picture(PictureShape),
     /// Table shape (not yet implemented)
     Table(TableShape),
-    /// Group shape containing other shapes (not yet implemented)
+    /// Group shape containing other shapes, which may be groups themselves
     Group(GroupShape<'a>),
     /// Line/connector shape (not yet implemented)
     Line(LineShape),
@@ -94,6 +96,55 @@ where
         }
     }
 
+    /// Get the common properties of the shape.
+    pub fn properties(&self) -> &ShapeProperties {
+        match self {
+            ShapeEnum::TextBox(tb) => Shape::properties(tb),
+            ShapeEnum::Placeholder(ph) => Shape::properties(ph),
+            ShapeEnum::AutoShape(as_) => Shape::properties(as_),
+            ShapeEnum::Picture(pic) => &pic.properties,
+            ShapeEnum::Table(table) => table.properties(),
+            ShapeEnum::Group(group) => group.properties(),
+            ShapeEnum::Line(line) => line.properties(),
+        }
+    }
+
+    /// Get the bounding box of the shape on the slide.
+    ///
+    /// The box is the unrotated frame of the shape; combine it with
+    /// [`rotation`](Self::rotation) and the flips to place a rotated shape.
+    /// Returns `None` if the shape has no anchor.
+    pub fn bounds(&self) -> Option<Rect> {
+        let props = self.properties();
+        if props.width == 0 && props.height == 0 {
+            return None;
+        }
+        Some(Rect::from_emus(
+            props.x as i64,
+            props.y as i64,
+            props.width as i64,
+            props.height as i64,
+        ))
+    }
+
+    /// Get the clockwise rotation of the shape in degrees.
+    #[inline]
+    pub fn rotation(&self) -> f64 {
+        self.properties().rotation
+    }
+
+    /// Check whether the shape is flipped horizontally.
+    #[inline]
+    pub fn flip_horizontal(&self) -> bool {
+        self.properties().flip_horizontal
+    }
+
+    /// Check whether the shape is flipped vertically.
+    #[inline]
+    pub fn flip_vertical(&self) -> bool {
+        self.properties().flip_vertical
+    }
+
     /// Get shape as TextBox if it is one.
     #[inline]
     pub fn as_textbox(&self) -> Option<&TextBox<'_>> {
@@ -121,6 +172,15 @@ where
         }
     }
 
+    /// Get shape as GroupShape if it is one.
+    #[inline]
+    pub fn as_group(&self) -> Option<&GroupShape<'a>> {
+        match self {
+            ShapeEnum::Group(group) => Some(group),
+            _ => None,
+        }
+    }
+
     /// Get shape as PictureShape if it is one.
     #[inline]
     pub fn as_picture(&self) -> Option<&PictureShape> {
@@ -144,34 +204,122 @@ where
 
 /// Table shape.
 ///
-/// Represents a table with rows and columns.
+/// Represents a table with rows and columns. PowerPoint stores tables as
+/// groups of cell text boxes, which are laid out on a grid by position.
 #[derive(Debug, Clone)]
 pub struct TableShape {
-    /// Shape ID
-    id: u32,
+    /// Shape properties
+    properties: ShapeProperties,
     /// Number of rows
     rows: usize,
     /// Number of columns
     columns: usize,
     /// Table cells (row-major order)
     cells: Vec<Vec<String>>,
+    /// Row and column span of each cell, `(0, 0)` for cells covered by a merged cell
+    spans: Vec<Vec<(usize, usize)>>,
 }
 
 impl TableShape {
     /// Create a new table shape.
     pub fn new(id: u32, rows: usize, columns: usize) -> Self {
         let cells = vec![vec![String::new(); columns]; rows];
+        let spans = vec![vec![(1, 1); columns]; rows];
         Self {
-            id,
+            properties: ShapeProperties {
+                id,
+                shape_type: ShapeType::Table,
+                ..Default::default()
+            },
             rows,
             columns,
             cells,
+            spans,
         }
     }
 
+    /// Build a table from a PowerPoint table group.
+    ///
+    /// Each cell is a text box inside the group; the border lines drawn
+    /// between cells are skipped.
+    pub(crate) fn from_escher(group: &EscherShape<'_>) -> Self {
+        let cells: Vec<_> = group
+            .child_shapes()
+            .iter()
+            .filter(|child| {
+                !matches!(
+                    child.shape_type(),
+                    EscherShapeType::Line | EscherShapeType::Group
+                )
+            })
+            .filter_map(|child| {
+                let anchor = *child.anchor()?;
+                Some((anchor, child.text().unwrap_or_default()))
+            })
+            .collect();
+
+        Self::from_cells(group.shape_id().unwrap_or(0), &cells)
+    }
+
+    /// Build a table from the bounds and text of its cell shapes.
+    ///
+    /// Grid columns and rows start at each distinct left and top edge; a
+    /// cell spanning several of them is a merged cell.
+    fn from_cells(id: u32, cells: &[(ShapeAnchor, String)]) -> Self {
+        let edges = |edge: fn(&ShapeAnchor) -> i32| {
+            let mut edges: Vec<i32> = cells.iter().map(|(anchor, _)| edge(anchor)).collect();
+            edges.sort_unstable();
+            edges.dedup();
+            edges
+        };
+        let lefts = edges(|a| a.left);
+        let tops = edges(|a| a.top);
+
+        let mut table = Self::new(id, tops.len(), lefts.len());
+        for (anchor, text) in cells {
+            let (Ok(row), Ok(col)) = (
+                tops.binary_search(&anchor.top),
+                lefts.binary_search(&anchor.left),
+            ) else {
+                continue;
+            };
+            let row_span = tops[row..]
+                .iter()
+                .take_while(|&&t| t < anchor.bottom)
+                .count()
+                .max(1);
+            let col_span = lefts[col..]
+                .iter()
+                .take_while(|&&l| l < anchor.right)
+                .count()
+                .max(1);
+
+            for r in row..row + row_span {
+                for c in col..col + col_span {
+                    table.spans[r][c] = (0, 0);
+                }
+            }
+            table.cells[row][col] = text.clone();
+            table.spans[row][col] = (row_span, col_span);
+        }
+        table
+    }
+
     /// Get shape ID.
     pub fn id(&self) -> u32 {
-        self.id
+        self.properties.id
+    }
+
+    /// Get the shape properties.
+    #[inline]
+    pub fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    /// Get the shape properties as mutable reference.
+    #[inline]
+    pub(crate) fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
     }
 
     /// Get number of rows.
@@ -191,6 +339,13 @@ impl TableShape {
             .and_then(|r| r.get(col))
             .map(|s| s.as_str())
     }
+
+    /// Get the number of rows and columns spanned by a cell.
+    ///
+    /// Cells covered by a merged cell span `(0, 0)`.
+    pub fn cell_span(&self, row: usize, col: usize) -> Option<(usize, usize)> {
+        self.spans.get(row).and_then(|r| r.get(col)).copied()
+    }
 }
 
 /// Group shape containing other shapes.
@@ -198,30 +353,22 @@ impl TableShape {
 /// Groups allow hierarchical organization of shapes.
 #[derive(Debug, Clone)]
 pub struct GroupShape<'a> {
-    /// Shape ID
-    id: u32,
+    /// Shape properties, including the group bounds
+    properties: ShapeProperties,
     /// Child shapes
     children: Vec<ShapeEnum<'a>>,
-    /// Left coordinate
-    left: i32,
-    /// Top coordinate
-    top: i32,
-    /// Width
-    width: i32,
-    /// Height
-    height: i32,
 }
 
 impl<'a> GroupShape<'a> {
     /// Create a new group shape.
     pub fn new(id: u32) -> Self {
         Self {
-            id,
+            properties: ShapeProperties {
+                id,
+                shape_type: ShapeType::Group,
+                ..Default::default()
+            },
             children: Vec::new(),
-            left: 0,
-            top: 0,
-            width: 0,
-            height: 0,
         }
     }
 
@@ -237,15 +384,27 @@ impl<'a> GroupShape<'a> {
 
     /// Set group bounds.
     pub fn set_bounds(&mut self, left: i32, top: i32, width: i32, height: i32) {
-        self.left = left;
-        self.top = top;
-        self.width = width;
-        self.height = height;
+        self.properties.x = left;
+        self.properties.y = top;
+        self.properties.width = width;
+        self.properties.height = height;
     }
 
     /// Get shape ID.
     pub fn id(&self) -> u32 {
-        self.id
+        self.properties.id
+    }
+
+    /// Get the shape properties.
+    #[inline]
+    pub fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    /// Get the shape properties as mutable reference.
+    #[inline]
+    pub(crate) fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
     }
 }
 
@@ -254,8 +413,8 @@ impl<'a> GroupShape<'a> {
 /// Represents a line or connector between two points.
 #[derive(Debug, Clone)]
 pub struct LineShape {
-    /// Shape ID
-    id: u32,
+    /// Shape properties
+    properties: ShapeProperties,
     /// Start X coordinate
     x1: i32,
     /// Start Y coordinate
@@ -272,9 +431,19 @@ pub struct LineShape {
 
 impl LineShape {
     /// Create a new line shape.
+    ///
+    /// The shape's bounds span both end points.
     pub fn new(id: u32, x1: i32, y1: i32, x2: i32, y2: i32) -> Self {
         Self {
-            id,
+            properties: ShapeProperties {
+                id,
+                shape_type: ShapeType::Line,
+                x: x1.min(x2),
+                y: y1.min(y2),
+                width: (x2 - x1).abs(),
+                height: (y2 - y1).abs(),
+                ..Default::default()
+            },
             x1,
             y1,
             x2,
@@ -296,7 +465,19 @@ impl LineShape {
 
     /// Get shape ID.
     pub fn id(&self) -> u32 {
-        self.id
+        self.properties.id
+    }
+
+    /// Get the shape properties.
+    #[inline]
+    pub fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    /// Get the shape properties as mutable reference.
+    #[inline]
+    pub(crate) fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
     }
 
     /// Get line length.
@@ -1,3 +1,4 @@
+use crate::common::trace;
 use crate::ole::consts::PptRecordType;
 /// SlideFactory - Creates slides from persist mapping with zero-copy parsing.
 ///
@@ -75,6 +76,7 @@ impl<'doc> SlideFactory<'doc> {
     /// Parse slide record at specific byte offset.
     fn parse_slide_at_offset(&self, offset: u32, persist_id: u32) -> Result<SlideData<'doc>> {
         let offset = offset as usize;
+        let _span = trace::debug_span!("parse_slide", persist_id, offset).entered();
 
         if offset + 8 > self.doc_data.len() {
             return Err(PptError::Corrupted(format!(
@@ -157,6 +159,17 @@ impl<'doc> SlideData<'doc> {
         self.doc_data
     }
 
+    /// Create a SlideData for a slide record read on its own, without the
+    /// rest of the document data.
+    pub(crate) fn detached(persist_id: u32, offset: usize, record: PptRecord) -> Self {
+        Self {
+            persist_id,
+            offset,
+            record,
+            doc_data: &[],
+        }
+    }
+
     /// Create a SlideData instance for testing purposes.
     ///
     /// # Note
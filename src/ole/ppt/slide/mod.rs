@@ -1,6 +1,7 @@
 //! Slide parsing and management with high-performance zero-copy design.
 
 pub mod factory;
+pub(crate) mod reader;
 pub mod types;
 
 // Re-export main types
@@ -0,0 +1,218 @@
+//! Incremental slide reader that reads the PowerPoint Document stream on demand.
+//!
+//! Instead of loading the whole stream, the reader follows the edit chain
+//! from the Current User stream to the persist directory, then reads each
+//! slide record only when it is asked for the next slide.
+
+use super::factory::SlideData;
+use super::types::Slide;
+use crate::ole::consts::PptRecordType;
+use crate::ole::ppt::package::Result;
+use crate::ole::ppt::parsers::PptRecordParser;
+use crate::ole::ppt::persist::{PersistMapping, PersistPtrHolder};
+use crate::ole::ppt::records::PptRecord;
+use crate::ole::{OleFile, StreamLocation};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Seek};
+
+/// Size of a PPT record header
+const RECORD_HEADER_SIZE: usize = 8;
+
+/// headerToken of an unencrypted CurrentUserAtom
+const CURRENT_USER_TOKEN: u32 = 0xE391_C05F;
+
+/// Reads slide text one slide at a time.
+///
+/// Each call to [`SlideTextReader::next_slide`] reads only the records of
+/// the next slide, so a caller that stops early never reads later slides.
+#[derive(Debug)]
+pub(crate) struct SlideTextReader {
+    /// Location of the PowerPoint Document stream
+    location: StreamLocation,
+    /// Persist ID to stream offset mapping of the current edit
+    persist_mapping: PersistMapping,
+    /// All persist IDs in ascending order; slides are the ones holding a Slide record
+    persist_ids: Vec<u32>,
+    /// Position in `persist_ids` of the next object to look at
+    next: usize,
+    /// Zero-based index of the next slide
+    slide_index: usize,
+    /// Outline text blocks of each slide, keyed by slide persist ID
+    outline_text: HashMap<u32, Vec<String>>,
+}
+
+impl SlideTextReader {
+    /// Prepare to read the slides of a presentation.
+    ///
+    /// Returns `None` when the presentation can't be read incrementally,
+    /// e.g. when its Current User stream or edit chain is missing, is
+    /// encrypted or is inconsistent; callers then load the whole presentation.
+    pub(crate) fn open<R: Read + Seek>(ole: &mut OleFile<R>) -> Result<Option<Self>> {
+        let Ok(location) = ole
+            .stream_location(&["PowerPoint Document"])
+            .or_else(|_| ole.stream_location(&["PP97_DUALSTORAGE", "PowerPoint Document"]))
+        else {
+            return Ok(None);
+        };
+        let Some(current_edit) = Self::current_edit_offset(ole) else {
+            return Ok(None);
+        };
+
+        // Walk the edit chain from the current edit back to the first one
+        let mut edits = Vec::new();
+        let mut visited = HashSet::new();
+        let mut offset = current_edit;
+        let mut doc_persist_id = None;
+        loop {
+            if !visited.insert(offset) {
+                return Ok(None);
+            }
+            let Some(record) = Self::read_record(ole, &location, offset)? else {
+                return Ok(None);
+            };
+            if record.record_type != PptRecordType::UserEditAtom || record.data.len() < 20 {
+                return Ok(None);
+            }
+            let field = |at: usize| {
+                u32::from_le_bytes([
+                    record.data[at],
+                    record.data[at + 1],
+                    record.data[at + 2],
+                    record.data[at + 3],
+                ])
+            };
+            doc_persist_id.get_or_insert(field(16));
+            edits.push(field(12));
+            match field(8) {
+                0 => break,
+                last_edit => offset = last_edit,
+            }
+        }
+
+        // Apply the persist directories oldest first so later edits win
+        let mut persist_mapping = PersistMapping::new();
+        for &directory in edits.iter().rev() {
+            let Some(record) = Self::read_record(ole, &location, directory)? else {
+                return Ok(None);
+            };
+            let Ok(holder) = PersistPtrHolder::parse(&record) else {
+                return Ok(None);
+            };
+            for (&persist_id, &offset) in holder.slide_locations() {
+                persist_mapping.add_mapping(persist_id, offset);
+            }
+        }
+
+        // Placeholder text lives in the Document's SlideListWithText
+        let Some(document_offset) = doc_persist_id.and_then(|id| persist_mapping.get_offset(id))
+        else {
+            return Ok(None);
+        };
+        let Some(document) = Self::read_record(ole, &location, document_offset)? else {
+            return Ok(None);
+        };
+        if document.record_type != PptRecordType::Document {
+            return Ok(None);
+        }
+        let outline_text = PptRecordParser::document_outline_text(&document);
+
+        let persist_ids = persist_mapping.get_persist_ids();
+
+        Ok(Some(Self {
+            location,
+            persist_mapping,
+            persist_ids,
+            next: 0,
+            slide_index: 0,
+            outline_text,
+        }))
+    }
+
+    /// Read the text of the next slide, with its zero-based index.
+    ///
+    /// Returns `None` once every slide has been read.
+    pub(crate) fn next_slide<R: Read + Seek>(
+        &mut self,
+        ole: &mut OleFile<R>,
+    ) -> Result<Option<(usize, String)>> {
+        while let Some(&persist_id) = self.persist_ids.get(self.next) {
+            self.next += 1;
+            let Some(offset) = self.persist_mapping.get_offset(persist_id) else {
+                continue;
+            };
+
+            // Skip notes, masters and other persist objects by their header
+            let mut header = [0u8; RECORD_HEADER_SIZE];
+            let read = ole.read_stream_range(&self.location, offset.into(), &mut header)?;
+            if read < RECORD_HEADER_SIZE
+                || u16::from_le_bytes([header[2], header[3]]) != PptRecordType::Slide as u16
+            {
+                continue;
+            }
+
+            let Some(record) = Self::read_record(ole, &self.location, offset)? else {
+                continue;
+            };
+            let outline_text = self
+                .outline_text
+                .get(&persist_id)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            let slide_data = SlideData::detached(persist_id, offset as usize, record);
+            let slide = Slide::from_slide_data(slide_data, self.slide_index + 1)
+                .with_outline_text(outline_text);
+            let text = slide.text()?.to_string();
+
+            let index = self.slide_index;
+            self.slide_index += 1;
+            return Ok(Some((index, text)));
+        }
+        Ok(None)
+    }
+
+    /// Read the offset of the current edit from the Current User stream.
+    fn current_edit_offset<R: Read + Seek>(ole: &mut OleFile<R>) -> Option<u32> {
+        let current_user = ole.open_stream(&["Current User"]).ok()?;
+        let field = |at: usize| {
+            current_user
+                .get(at..at + 4)
+                .map(|bytes| u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        };
+
+        // The atom header is followed by size, headerToken and offsetToCurrentEdit
+        let record_type = current_user.get(2..4)?;
+        if u16::from_le_bytes([record_type[0], record_type[1]])
+            != PptRecordType::CurrentUserAtom as u16
+            || field(12)? != CURRENT_USER_TOKEN
+        {
+            return None;
+        }
+        field(16)
+    }
+
+    /// Read and parse the record at `offset` of the document stream.
+    ///
+    /// Returns `None` when the offset is past the end of the stream.
+    fn read_record<R: Read + Seek>(
+        ole: &mut OleFile<R>,
+        location: &StreamLocation,
+        offset: u32,
+    ) -> Result<Option<PptRecord>> {
+        let offset = u64::from(offset);
+        let mut header = [0u8; RECORD_HEADER_SIZE];
+        if ole.read_stream_range(location, offset, &mut header)? < RECORD_HEADER_SIZE {
+            return Ok(None);
+        }
+
+        // The declared length is not trusted beyond the end of the stream
+        let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let available = location.size() - offset;
+        let size = (RECORD_HEADER_SIZE as u64 + u64::from(length)).min(available) as usize;
+
+        let mut data = vec![0u8; size];
+        let read = ole.read_stream_range(location, offset, &mut data)?;
+        data.truncate(read);
+        let (record, _consumed) = PptRecord::parse(&data, 0)?;
+        Ok(Some(record))
+    }
+}
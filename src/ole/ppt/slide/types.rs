@@ -1,7 +1,9 @@
 /// High-performance Slide implementation with lazy shape loading and zero-copy design.
+use super::super::escher::EscherTextOptions;
 use super::super::package::Result;
 use super::super::records::PptRecord;
 use super::super::shapes::ShapeEnum;
+use super::super::shapes::shape_enum::TableShape;
 use super::factory::SlideData;
 use crate::ole::consts::PptRecordType;
 use once_cell::unsync::OnceCell;
@@ -23,6 +25,8 @@ pub struct Slide<'doc> {
     /// Reference to document data for lazy shape parsing (reserved for future use)
     #[allow(dead_code)]
     doc_data: &'doc [u8],
+    /// Outline text blocks of this slide, referenced by placeholder shapes
+    outline_text: &'doc [String],
     /// Lazily-loaded shapes (use 'static since they store owned data)
     shapes: OnceCell<Vec<ShapeEnum<'static>>>,
     /// Cached text content
@@ -37,12 +41,22 @@ impl<'doc> Slide<'doc> {
             persist_id: data.persist_id,
             slide_number,
             doc_data: doc_data_ref,
+            outline_text: &[],
             record: data.record,
             shapes: OnceCell::new(),
             text_cache: OnceCell::new(),
         }
     }
 
+    /// Attach the slide's outline text blocks from the SlideListWithText.
+    ///
+    /// Placeholder shapes refer to these blocks instead of holding their
+    /// text, so without them placeholder text is missing.
+    pub(crate) fn with_outline_text(mut self, outline_text: &'doc [String]) -> Self {
+        self.outline_text = outline_text;
+        self
+    }
+
     /// Get the slide number (1-based).
     #[inline]
     pub fn slide_number(&self) -> usize {
@@ -87,6 +101,65 @@ impl<'doc> Slide<'doc> {
             .map(|s| s.as_str())
     }
 
+    /// Get the title of this slide.
+    ///
+    /// The title is read from the shape whose TextHeaderAtom marks its text
+    /// as a title or center title, falling back to the title placeholder.
+    /// Returns `None` if the slide has no title shape.
+    pub fn title(&self) -> Result<Option<String>> {
+        let Some(ppdrawing) = self
+            .record
+            .find_child(crate::ole::consts::PptRecordType::PPDrawing)
+        else {
+            return Ok(None);
+        };
+
+        let title = super::super::escher::extract_title_from_escher_with_outline(
+            &ppdrawing.data,
+            self.outline_text,
+        )?;
+        Ok(title
+            .map(|title| title.trim().to_string())
+            .filter(|title| !title.is_empty()))
+    }
+
+    /// Extract the text of this slide, leaving out the title.
+    ///
+    /// The title is available through [`Slide::title`].
+    pub fn body_text(&self) -> Result<String> {
+        self.extract_text(EscherTextOptions {
+            skip_titles: true,
+            ..EscherTextOptions::default()
+        })
+    }
+
+    /// Get the tables on this slide, including tables inside groups.
+    pub fn tables(&self) -> Result<Vec<&TableShape>> {
+        fn collect<'s>(shapes: &'s [ShapeEnum<'static>], tables: &mut Vec<&'s TableShape>) {
+            for shape in shapes {
+                match shape {
+                    ShapeEnum::Table(table) => tables.push(table),
+                    ShapeEnum::Group(group) => collect(group.children(), tables),
+                    _ => {},
+                }
+            }
+        }
+
+        let mut tables = Vec::new();
+        collect(self.shapes()?, &mut tables);
+        Ok(tables)
+    }
+
+    /// Extract the text of this slide, leaving out table cells.
+    ///
+    /// Table content is available through [`Slide::tables`].
+    pub fn text_without_tables(&self) -> Result<String> {
+        self.extract_text(EscherTextOptions {
+            skip_tables: true,
+            ..EscherTextOptions::default()
+        })
+    }
+
     /// Parse shapes from PPDrawing record.
     ///
     /// # Performance
@@ -107,14 +180,18 @@ impl<'doc> Slide<'doc> {
             None => return Ok(Vec::new()),
         };
 
-        // Extract Escher shapes from PPDrawing data
+        // Extract the tree of Escher shapes from PPDrawing data, keeping groups
         let escher_shapes =
-            super::super::escher::EscherShapeFactory::extract_shapes_from_drawing(&ppdrawing.data)?;
+            super::super::escher::EscherShapeFactory::extract_shape_tree_from_drawing(
+                &ppdrawing.data,
+            )?;
 
         // Convert Escher shapes to ShapeEnum with full property extraction
         let shapes: Vec<ShapeEnum<'static>> = escher_shapes
             .iter()
-            .filter_map(|escher_shape| Self::convert_escher_to_shape_enum(escher_shape))
+            .filter_map(|escher_shape| {
+                Self::convert_escher_to_shape_enum(escher_shape, self.outline_text)
+            })
             .collect();
 
         Ok(shapes)
@@ -126,34 +203,43 @@ impl<'doc> Slide<'doc> {
     ///
     /// - Direct property access (no allocations)
     /// - Pattern matching for type dispatch
+    ///
+    /// Group members are converted recursively, and text referenced by an
+    /// OutlineTextRefAtom is taken from `outline_text`.
     fn convert_escher_to_shape_enum(
         escher_shape: &super::super::escher::EscherShape<'_>,
+        outline_text: &[String],
     ) -> Option<ShapeEnum<'static>> {
         use super::super::escher::EscherShapeType;
         use super::super::shapes::*;
 
         let shape_id = escher_shape.shape_id().unwrap_or(0);
-        let anchor = escher_shape.anchor();
+        let text = || escher_shape.text_with_outline(outline_text);
+
+        // Placeholders are recognised by their OEPlaceholderAtom, whatever
+        // their geometry
+        if let Some(placeholder_id) = escher_shape.placeholder_id()
+            && escher_shape.shape_type() != EscherShapeType::Group
+        {
+            let properties = Self::shape_properties(escher_shape, shape::ShapeType::Placeholder);
+            let mut placeholder = Placeholder::new(properties, Vec::new());
+            placeholder.set_placeholder_type(
+                Placeholder::map_poi_placeholder_id_to_type(placeholder_id as u16)
+                    .unwrap_or(PlaceholderType::Custom(placeholder_id as u16)),
+            );
+            if let Some(text) = text() {
+                placeholder.set_text(text);
+            }
+            return Some(ShapeEnum::Placeholder(placeholder));
+        }
 
         match escher_shape.shape_type() {
             EscherShapeType::TextBox => {
                 // Create TextBox with proper properties
-                let mut properties = shape::ShapeProperties {
-                    id: shape_id,
-                    shape_type: shape::ShapeType::TextBox,
-                    ..Default::default()
-                };
-
-                // Set coordinates if anchor exists
-                if let Some(a) = anchor {
-                    properties.x = a.left;
-                    properties.y = a.top;
-                    properties.width = a.width();
-                    properties.height = a.height();
-                }
+                let properties = Self::shape_properties(escher_shape, shape::ShapeType::TextBox);
 
                 // Extract text from shape
-                let text = escher_shape.text().unwrap_or_default();
+                let text = text().unwrap_or_default();
 
                 let mut textbox = TextBox::new(properties, Vec::new());
                 if !text.is_empty() {
@@ -165,11 +251,9 @@ impl<'doc> Slide<'doc> {
 
             EscherShapeType::Picture => {
                 // Create PictureShape
-                let mut picture = crate::ole::ppt::shapes::PictureShape::new(shape_id);
-
-                if let Some(a) = anchor {
-                    picture.set_bounds(a.left, a.top, a.width(), a.height());
-                }
+                let mut picture = crate::ole::ppt::shapes::PictureShape::from_properties(
+                    Self::shape_properties(escher_shape, shape::ShapeType::Picture),
+                );
 
                 // Extract blip ID from properties
                 use super::super::escher::EscherPropertyId;
@@ -185,44 +269,62 @@ impl<'doc> Slide<'doc> {
 
             EscherShapeType::Line => {
                 // Create LineShape
-                if let Some(a) = anchor {
-                    let mut line =
-                        shape_enum::LineShape::new(shape_id, a.left, a.top, a.right, a.bottom);
-
-                    // Extract line properties
-                    use super::super::escher::EscherPropertyId;
-                    if let Some(width) = escher_shape
-                        .properties()
-                        .get_int(EscherPropertyId::LineWidth)
-                    {
-                        line.set_width(width);
-                    }
-                    if let Some(color) = escher_shape
-                        .properties()
-                        .get_color(EscherPropertyId::LineColor)
-                    {
-                        line.set_color(color);
-                    }
-
-                    Some(ShapeEnum::Line(line))
+                escher_shape.anchor()?;
+                let properties = Self::shape_properties(escher_shape, shape::ShapeType::Line);
+
+                // Flips swap the end points within the bounding box
+                let (left, right) = (properties.x, properties.x + properties.width);
+                let (top, bottom) = (properties.y, properties.y + properties.height);
+                let (x1, x2) = if properties.flip_horizontal {
+                    (right, left)
+                } else {
+                    (left, right)
+                };
+                let (y1, y2) = if properties.flip_vertical {
+                    (bottom, top)
                 } else {
-                    None
+                    (top, bottom)
+                };
+                let mut line = shape_enum::LineShape::new(shape_id, x1, y1, x2, y2);
+                *line.properties_mut() = properties;
+
+                // Extract line properties
+                use super::super::escher::EscherPropertyId;
+                if let Some(width) = escher_shape
+                    .properties()
+                    .get_int(EscherPropertyId::LineWidth)
+                {
+                    line.set_width(width);
+                }
+                if let Some(color) = escher_shape
+                    .properties()
+                    .get_color(EscherPropertyId::LineColor)
+                {
+                    line.set_color(color);
                 }
+
+                Some(ShapeEnum::Line(line))
+            },
+
+            EscherShapeType::Group if escher_shape.is_table() => {
+                let mut table = shape_enum::TableShape::from_escher(escher_shape);
+                *table.properties_mut() =
+                    Self::shape_properties(escher_shape, shape::ShapeType::Table);
+                Some(ShapeEnum::Table(table))
             },
 
             EscherShapeType::Group => {
                 // Create GroupShape and parse children recursively
                 let mut group = shape_enum::GroupShape::new(shape_id);
-
-                if let Some(a) = anchor {
-                    group.set_bounds(a.left, a.top, a.width(), a.height());
-                }
-
-                // Recursively parse child shapes
-                // This follows Apache POI's approach: iterate child shapes and convert them
-                let child_escher_shapes = escher_shape.child_shapes();
-                for child_escher in &child_escher_shapes {
-                    if let Some(child_shape) = Self::convert_escher_to_shape_enum(child_escher) {
+                *group.properties_mut() =
+                    Self::shape_properties(escher_shape, shape::ShapeType::Group);
+
+                // Recursively convert the members, which the shape tree has
+                // already placed in slide coordinates
+                for child_escher in &escher_shape.children {
+                    if let Some(child_shape) =
+                        Self::convert_escher_to_shape_enum(child_escher, outline_text)
+                    {
                         group.add_child(child_shape);
                     }
                 }
@@ -232,20 +334,12 @@ impl<'doc> Slide<'doc> {
 
             EscherShapeType::Rectangle | EscherShapeType::Ellipse | EscherShapeType::AutoShape => {
                 // Create AutoShape
-                let mut properties = shape::ShapeProperties {
-                    id: shape_id,
-                    shape_type: shape::ShapeType::AutoShape,
-                    ..Default::default()
-                };
+                let properties = Self::shape_properties(escher_shape, shape::ShapeType::AutoShape);
 
-                if let Some(a) = anchor {
-                    properties.x = a.left;
-                    properties.y = a.top;
-                    properties.width = a.width();
-                    properties.height = a.height();
+                let mut autoshape = AutoShape::new(properties, Vec::new());
+                if let Some(text) = text() {
+                    autoshape.set_text(text);
                 }
-
-                let autoshape = AutoShape::new(properties, Vec::new());
                 Some(ShapeEnum::AutoShape(autoshape))
             },
 
@@ -254,8 +348,60 @@ impl<'doc> Slide<'doc> {
         }
     }
 
+    /// Build the common properties of a shape: its bounds in EMUs, rotation
+    /// and flips.
+    ///
+    /// Anchors are stored in master units. Shapes rotated by 45 to 135 or
+    /// 225 to 315 degrees store their anchor turned by 90 degrees around its
+    /// centre, so the frame is turned back to match the unrotated shape.
+    fn shape_properties(
+        escher_shape: &super::super::escher::EscherShape<'_>,
+        shape_type: crate::ole::ppt::shapes::shape::ShapeType,
+    ) -> crate::ole::ppt::shapes::shape::ShapeProperties {
+        use crate::common::unit::ppt_master_i64_to_emu_i32;
+
+        let mut properties = crate::ole::ppt::shapes::shape::ShapeProperties {
+            id: escher_shape.shape_id().unwrap_or(0),
+            shape_type,
+            rotation: escher_shape.rotation() as f64,
+            flip_horizontal: escher_shape.flip_horizontal(),
+            flip_vertical: escher_shape.flip_vertical(),
+            ..Default::default()
+        };
+
+        if let Some(anchor) = escher_shape.anchor() {
+            let (mut left, mut top) = (anchor.left as i64, anchor.top as i64);
+            let (mut width, mut height) = (anchor.width() as i64, anchor.height() as i64);
+
+            let rotation = properties.rotation.rem_euclid(360.0);
+            if (45.0..135.0).contains(&rotation) || (225.0..315.0).contains(&rotation) {
+                left += (width - height) / 2;
+                top += (height - width) / 2;
+                std::mem::swap(&mut width, &mut height);
+            }
+
+            properties.x = ppt_master_i64_to_emu_i32(left);
+            properties.y = ppt_master_i64_to_emu_i32(top);
+            properties.width = ppt_master_i64_to_emu_i32(width);
+            properties.height = ppt_master_i64_to_emu_i32(height);
+        }
+
+        properties
+    }
+
     /// Extract all text from slide and its shapes.
     fn extract_all_text(&self) -> Result<String> {
+        self.extract_text(EscherTextOptions::default())
+    }
+
+    /// Extract text from slide records and from the drawing with `options`.
+    ///
+    /// Outline text referenced by placeholders is always resolved.
+    fn extract_text(&self, options: EscherTextOptions) -> Result<String> {
+        let options = EscherTextOptions {
+            outline_text: self.outline_text,
+            ..options
+        };
         let mut text_parts = Vec::new();
 
         // 1. Extract text from direct slide records (TextCharsAtom, etc.)
@@ -272,7 +418,10 @@ impl<'doc> Slide<'doc> {
         if let Some(ppdrawing) = self
             .record
             .find_child(crate::ole::consts::PptRecordType::PPDrawing)
-            && let Ok(escher_text) = super::super::escher::extract_text_from_escher(&ppdrawing.data)
+            && let Ok(escher_text) = super::super::escher::extract_text_from_escher_with_options(
+                &ppdrawing.data,
+                &options,
+            )
         {
             let trimmed = escher_text.trim();
             if !trimmed.is_empty() {
@@ -1002,4 +1151,325 @@ mod tests {
         assert!(pos1 < pos2);
         assert!(pos2 < pos3);
     }
+
+    /// Build PPDrawing data holding a title text box and a 2x2 table whose
+    /// first row is a single merged cell.
+    fn create_table_drawing() -> Vec<u8> {
+        use crate::ole::escher::writer::*;
+
+        fn text_shape(id: u32, anchor: Option<(i32, i32, i32, i32)>, text: &str) -> Vec<u8> {
+            let mut chars = Vec::new();
+            for unit in text.encode_utf16() {
+                chars.extend_from_slice(&unit.to_le_bytes());
+            }
+            let mut textbox = Vec::new();
+            write_atom(&mut textbox, 0, 0, 0x0FA0, &chars).unwrap();
+
+            let mut sp = Vec::new();
+            ShapeBuilder::new(202, id).write(&mut sp).unwrap();
+            if let Some((left, top, right, bottom)) = anchor {
+                write_child_anchor(&mut sp, left, top, right, bottom).unwrap();
+            }
+            write_container(&mut sp, 0, 0xF00D, &textbox).unwrap();
+
+            let mut out = Vec::new();
+            write_container(&mut out, 0, 0xF004, &sp).unwrap();
+            out
+        }
+
+        // Table group: its own shape record carries tableProperties = 1
+        let mut table_props = Vec::new();
+        table_props.extend_from_slice(&0x039Fu16.to_le_bytes());
+        table_props.extend_from_slice(&1i32.to_le_bytes());
+        let mut table_sp = Vec::new();
+        write_spgr(&mut table_sp, 0, 0, 200, 100).unwrap();
+        ShapeBuilder::new(0, 1025)
+            .with_flags(0x201)
+            .write(&mut table_sp)
+            .unwrap();
+        write_atom(&mut table_sp, 3, 1, 0xF122, &table_props).unwrap();
+
+        let mut table = Vec::new();
+        write_container(&mut table, 0, 0xF004, &table_sp).unwrap();
+        table.extend(text_shape(1026, Some((0, 0, 200, 50)), "Header"));
+        table.extend(text_shape(1027, Some((0, 50, 100, 100)), "A"));
+        table.extend(text_shape(1028, Some((100, 50, 200, 100)), "B"));
+
+        let mut root_sp = Vec::new();
+        ShapeBuilder::new(0, 1024)
+            .with_flags(0x005)
+            .write(&mut root_sp)
+            .unwrap();
+
+        let mut patriarch = Vec::new();
+        write_container(&mut patriarch, 0, 0xF004, &root_sp).unwrap();
+        patriarch.extend(text_shape(1029, None, "Title"));
+        write_container(&mut patriarch, 0, 0xF003, &table).unwrap();
+
+        let mut spgr = Vec::new();
+        write_container(&mut spgr, 0, 0xF003, &patriarch).unwrap();
+        let mut drawing = Vec::new();
+        write_container(&mut drawing, 0, 0xF002, &spgr).unwrap();
+        drawing
+    }
+
+    #[test]
+    fn test_slide_tables() {
+        let doc_data = vec![0u8; 64];
+        let ppdrawing =
+            create_test_record(PptRecordType::PPDrawing, create_table_drawing(), Vec::new());
+        let record = create_test_record(PptRecordType::Slide, vec![0u8; 8], vec![ppdrawing]);
+        let slide = Slide::from_slide_data(create_slide_data(record, 256, &doc_data), 1);
+
+        let tables = slide.tables().unwrap();
+        assert_eq!(tables.len(), 1);
+
+        let table = tables[0];
+        assert_eq!(table.rows(), 2);
+        assert_eq!(table.columns(), 2);
+        assert_eq!(table.cell(0, 0), Some("Header"));
+        assert_eq!(table.cell_span(0, 0), Some((1, 2)));
+        assert_eq!(table.cell_span(0, 1), Some((0, 0)));
+        assert_eq!(table.cell(1, 0), Some("A"));
+        assert_eq!(table.cell(1, 1), Some("B"));
+
+        assert!(slide.text().unwrap().contains("Header"));
+        let body = slide.text_without_tables().unwrap();
+        assert!(body.contains("Title"));
+        assert!(!body.contains("Header"));
+    }
+
+    /// Build PPDrawing data holding one text box per `(text type, placeholder, text)`.
+    ///
+    /// A text type adds a TextHeaderAtom to the text box; a placeholder id
+    /// adds an OEPlaceholderAtom to the shape's client data.
+    fn create_text_drawing(shapes: &[(Option<u32>, Option<u8>, &str)]) -> Vec<u8> {
+        use crate::ole::escher::writer::*;
+
+        let mut root_sp = Vec::new();
+        ShapeBuilder::new(0, 1024)
+            .with_flags(0x005)
+            .write(&mut root_sp)
+            .unwrap();
+        let mut patriarch = Vec::new();
+        write_container(&mut patriarch, 0, 0xF004, &root_sp).unwrap();
+
+        for (idx, (text_type, placeholder, text)) in shapes.iter().enumerate() {
+            let mut textbox = Vec::new();
+            if let Some(text_type) = text_type {
+                write_atom(&mut textbox, 0, 0, 3999, &text_type.to_le_bytes()).unwrap();
+            }
+            let chars: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+            write_atom(&mut textbox, 0, 0, 0x0FA0, &chars).unwrap();
+
+            let mut sp = Vec::new();
+            ShapeBuilder::new(202, 1025 + idx as u32)
+                .write(&mut sp)
+                .unwrap();
+            if let Some(placeholder) = placeholder {
+                let mut client_data = Vec::new();
+                write_atom(
+                    &mut client_data,
+                    0,
+                    0,
+                    3011,
+                    &[0, 0, 0, 0, *placeholder, 0, 0, 0],
+                )
+                .unwrap();
+                write_container(&mut sp, 0, 0xF011, &client_data).unwrap();
+            }
+            write_container(&mut sp, 0, 0xF00D, &textbox).unwrap();
+            write_container(&mut patriarch, 0, 0xF004, &sp).unwrap();
+        }
+
+        let mut spgr = Vec::new();
+        write_container(&mut spgr, 0, 0xF003, &patriarch).unwrap();
+        let mut drawing = Vec::new();
+        write_container(&mut drawing, 0, 0xF002, &spgr).unwrap();
+        drawing
+    }
+
+    fn create_slide_with_drawing_data(drawing: Vec<u8>) -> PptRecord {
+        let ppdrawing = create_test_record(PptRecordType::PPDrawing, drawing, Vec::new());
+        create_test_record(PptRecordType::Slide, vec![0u8; 8], vec![ppdrawing])
+    }
+
+    #[test]
+    fn test_slide_title_from_text_header() {
+        let doc_data = vec![0u8; 64];
+        // The title is stored between two body text boxes
+        let drawing = create_text_drawing(&[
+            (Some(1), None, "First point"),
+            (Some(0), None, "Quarterly Review"),
+            (Some(4), None, "Footnote"),
+        ]);
+        let record = create_slide_with_drawing_data(drawing);
+        let slide = Slide::from_slide_data(create_slide_data(record, 256, &doc_data), 1);
+
+        assert_eq!(slide.title().unwrap().as_deref(), Some("Quarterly Review"));
+        assert!(slide.text().unwrap().contains("Quarterly Review"));
+
+        let body = slide.body_text().unwrap();
+        assert_eq!(body, "First point\nFootnote");
+    }
+
+    #[test]
+    fn test_slide_title_from_placeholder() {
+        let doc_data = vec![0u8; 64];
+        let drawing = create_text_drawing(&[
+            (None, Some(14), "Body text"),
+            (None, Some(15), "Centered Title"),
+        ]);
+        let record = create_slide_with_drawing_data(drawing);
+        let slide = Slide::from_slide_data(create_slide_data(record, 256, &doc_data), 1);
+
+        assert_eq!(slide.title().unwrap().as_deref(), Some("Centered Title"));
+        assert_eq!(slide.body_text().unwrap(), "Body text");
+
+        let record = create_slide_with_text();
+        let slide = Slide::from_slide_data(create_slide_data(record, 256, &doc_data), 1);
+        assert_eq!(slide.title().unwrap(), None);
+        assert_eq!(slide.body_text().unwrap(), "Test");
+    }
+
+    #[test]
+    fn test_slide_shape_geometry() {
+        use crate::common::Rect;
+        use crate::ole::escher::writer::*;
+
+        let doc_data = vec![0u8; 64];
+
+        // Rectangle at (1in, 1in), 2in x 0.5in, rotated by 90 degrees and
+        // flipped horizontally. The anchor is stored turned by 90 degrees.
+        let mut rect_sp = Vec::new();
+        ShapeBuilder::new(1, 1025)
+            .with_flags(0xA40)
+            .write(&mut rect_sp)
+            .unwrap();
+        let mut props = PropertyBuilder::new();
+        props.add_simple(0x0004, 90 << 16);
+        props.write(&mut rect_sp).unwrap();
+        let anchor: Vec<u8> = [144i16, 1008, 1296, 1296]
+            .iter()
+            .flat_map(|v| v.to_le_bytes())
+            .collect();
+        write_atom(&mut rect_sp, 0, 0, 0xF010, &anchor).unwrap();
+
+        // Group at (0, 2in), 1in x 1in, whose child space is 1000 x 1000
+        let mut group_sp = Vec::new();
+        write_spgr(&mut group_sp, 0, 0, 1000, 1000).unwrap();
+        ShapeBuilder::new(0, 1026)
+            .with_flags(0x201)
+            .write(&mut group_sp)
+            .unwrap();
+        write_client_anchor(&mut group_sp, 0, 1152, 576, 1728).unwrap();
+        let mut child_sp = Vec::new();
+        ShapeBuilder::new(1, 1027)
+            .with_flags(0xA00)
+            .write(&mut child_sp)
+            .unwrap();
+        write_child_anchor(&mut child_sp, 500, 0, 1000, 500).unwrap();
+        let mut group = Vec::new();
+        write_container(&mut group, 0, 0xF004, &group_sp).unwrap();
+        write_container(&mut group, 0, 0xF004, &child_sp).unwrap();
+
+        let mut root_sp = Vec::new();
+        ShapeBuilder::new(0, 1024)
+            .with_flags(0x005)
+            .write(&mut root_sp)
+            .unwrap();
+        let mut patriarch = Vec::new();
+        write_container(&mut patriarch, 0, 0xF004, &root_sp).unwrap();
+        write_container(&mut patriarch, 0, 0xF004, &rect_sp).unwrap();
+        write_container(&mut patriarch, 0, 0xF003, &group).unwrap();
+        let mut spgr = Vec::new();
+        write_container(&mut spgr, 0, 0xF003, &patriarch).unwrap();
+        let mut drawing = Vec::new();
+        write_container(&mut drawing, 0, 0xF002, &spgr).unwrap();
+
+        let record = create_slide_with_drawing_data(drawing);
+        let slide = Slide::from_slide_data(create_slide_data(record, 256, &doc_data), 1);
+        let shapes = slide.shapes().unwrap();
+        let find = |id: u32| shapes.iter().find(|shape| shape.properties().id == id);
+
+        let rect = find(1025).unwrap();
+        assert_eq!(
+            rect.bounds(),
+            Some(Rect::from_emus(914400, 914400, 1828800, 457200))
+        );
+        assert_eq!(rect.rotation(), 90.0);
+        assert!(rect.flip_horizontal());
+        assert!(!rect.flip_vertical());
+
+        let group = find(1026).unwrap();
+        assert_eq!(
+            group.bounds(),
+            Some(Rect::from_emus(0, 1828800, 914400, 914400))
+        );
+
+        // Group members are kept inside their group
+        assert!(find(1027).is_none());
+        let children = group.as_group().unwrap().children();
+        assert_eq!(children.len(), 1);
+        let child = &children[0];
+        assert_eq!(child.properties().id, 1027);
+        assert_eq!(
+            child.bounds(),
+            Some(Rect::from_emus(457200, 1828800, 457200, 457200))
+        );
+        assert_eq!(child.rotation(), 0.0);
+
+        // The patriarch is unwrapped into the top-level shapes
+        assert!(find(1024).is_none());
+        assert_eq!(shapes.len(), 2);
+    }
+
+    #[test]
+    fn test_nested_group_shapes() {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/ole/ppt/NestedGroups.ppt");
+        let presentation = crate::ole::ppt::Package::open(path)
+            .unwrap()
+            .presentation()
+            .unwrap();
+        let slides = presentation.slides().unwrap();
+        let slide = &slides[0];
+
+        // Text boxes at every depth of the three nested groups
+        let levels = [
+            "Top level text",
+            "Level one text",
+            "Level two text",
+            "Level three text",
+        ];
+        let text = slide.text().unwrap();
+        for level in levels {
+            assert!(text.contains(level), "missing {level:?} in {text:?}");
+        }
+
+        // The shapes keep the group hierarchy
+        let shapes = slide.shapes().unwrap();
+        // The slide background follows the shape group of the drawing
+        assert_eq!(shapes.len(), 3);
+        assert_eq!(shapes[0].text().unwrap(), levels[0]);
+        let mut group = shapes[1].as_group().unwrap();
+        for (depth, level) in levels.iter().enumerate().skip(1) {
+            let children = group.children();
+            assert_eq!(children[0].text().unwrap(), *level);
+            if depth == levels.len() - 1 {
+                assert_eq!(children.len(), 1);
+            } else {
+                assert_eq!(children.len(), 2);
+                group = children[1].as_group().unwrap();
+            }
+        }
+
+        // The innermost text box is placed in slide coordinates
+        assert_eq!(
+            group.children()[0].bounds(),
+            Some(crate::common::Rect::from_emus(
+                1016000, 3556000, 2540000, 508000
+            ))
+        );
+    }
 }
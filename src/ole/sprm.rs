@@ -153,22 +153,22 @@ fn parse_sprms_two_byte(grpprl: &[u8]) -> Vec<Sprm> {
             2 | 4 | 5 => 2, // 2 byte operand
             3 => 4,         // 4 byte operand
             6 => {
-                // Variable length - read size from first byte (or 2 bytes for long SPRMs)
-                if offset + 1 < grpprl.len() {
-                    // Check if this is a long SPRM (SPRM_LONG_PARAGRAPH or SPRM_LONG_TABLE)
-                    if opcode == 0xc615 || opcode == 0xd608 {
-                        // Long SPRM - operand size in next 2 bytes
-                        if offset + 3 <= grpprl.len() {
-                            read_u16_le(grpprl, offset).unwrap_or(0) as usize
-                        } else {
-                            break;
-                        }
-                    } else {
-                        // Regular variable SPRM - size in first byte
-                        grpprl[offset] as usize
-                    }
+                // Variable length - the operand is prefixed by its size, which
+                // is not part of the operand itself
+                if opcode == 0xc615 || opcode == 0xd608 {
+                    // Long SPRM - 2-byte size that counts one byte more than follows
+                    let Ok(size) = read_u16_le(grpprl, offset) else {
+                        break;
+                    };
+                    offset += 2;
+                    (size as usize).saturating_sub(1)
                 } else {
-                    break;
+                    // Regular variable SPRM - 1-byte size
+                    let Some(&size) = grpprl.get(offset) else {
+                        break;
+                    };
+                    offset += 1;
+                    size as usize
                 }
             },
             7 => 3, // 3 byte operand
@@ -267,6 +267,26 @@ mod tests {
         assert_eq!(sprms[1].opcode, 0x4A43); // Font size (0x43, 0x4A bytes → 0x4A43 LE)
     }
 
+    #[test]
+    fn test_parse_variable_sprms() {
+        let grpprl = vec![
+            0x4D, 0xC6, // sprmPShd, variable
+            0x02, 0xAA, 0xBB, // size 2, operand
+            0x08, 0xD6, // sprmTDefTable, long variable
+            0x04, 0x00, // size counts one byte more than follows
+            0x01, 0x10, 0x20, // operand
+            0x35, 0x08, 0x01, // sprmCFBold
+        ];
+
+        let sprms = parse_sprms(&grpprl);
+        assert_eq!(sprms.len(), 3);
+        assert_eq!(sprms[0].operand_bytes(), &[0xAA, 0xBB]);
+        assert_eq!(sprms[0].size, 5);
+        assert_eq!(sprms[1].operand_bytes(), &[0x01, 0x10, 0x20]);
+        assert_eq!((sprms[1].offset, sprms[1].size), (5, 7));
+        assert_eq!(sprms[2].opcode, 0x0835);
+    }
+
     #[test]
     fn test_find_sprm() {
         let sprms = vec![
@@ -805,6 +805,36 @@ pub const SPRM_S_DXA_COLUMNS: u16 = 0x9003;
 /// Encodes to: 001_100_0_000001110 = 0x300E
 pub const SPRM_S_GPRF_IHDT: u16 = 0x300E;
 
+/// sprmSDyaHdrTop - Header distance from the top edge (operation 0x17)
+pub const SPRM_S_DYA_HDR_TOP: u16 = 0xB017;
+
+/// sprmSDyaHdrBottom - Footer distance from the bottom edge (operation 0x18)
+pub const SPRM_S_DYA_HDR_BOTTOM: u16 = 0xB018;
+
+/// sprmSBOrientation - Page orientation, 1 = portrait, 2 = landscape (operation 0x1D)
+pub const SPRM_S_B_ORIENTATION: u16 = 0x301D;
+
+/// sprmSXaPage - Page width (operation 0x1F)
+pub const SPRM_S_XA_PAGE: u16 = 0xB01F;
+
+/// sprmSYaPage - Page height (operation 0x20)
+pub const SPRM_S_YA_PAGE: u16 = 0xB020;
+
+/// sprmSDxaLeft - Left margin (operation 0x21)
+pub const SPRM_S_DXA_LEFT: u16 = 0xB021;
+
+/// sprmSDxaRight - Right margin (operation 0x22)
+pub const SPRM_S_DXA_RIGHT: u16 = 0xB022;
+
+/// sprmSDyaTop - Top margin (operation 0x23)
+pub const SPRM_S_DYA_TOP: u16 = 0x9023;
+
+/// sprmSDyaBottom - Bottom margin (operation 0x24)
+pub const SPRM_S_DYA_BOTTOM: u16 = 0x9024;
+
+/// sprmSDzaGutter - Gutter width (operation 0x25)
+pub const SPRM_S_DZA_GUTTER: u16 = 0xB025;
+
 /// Extract SPRM type from opcode (bits 10-12).
 ///
 /// Returns:
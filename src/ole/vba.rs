@@ -0,0 +1,582 @@
+//! VBA project parsing (MS-OVBA).
+//!
+//! Macro-enabled Office Open XML files (.docm, .xlsm, .pptm) carry their
+//! macros in a `vbaProject.bin` part, which is a compound file of its own.
+//! Legacy binary files keep the same storage inside the document: Word uses
+//! `Macros` and Excel uses `_VBA_PROJECT_CUR`.
+//!
+//! The project storage holds a `VBA` storage whose compressed `dir` stream
+//! describes the project, its references and its modules. Each module's
+//! source code is stored compressed in its own stream, after the p-code
+//! section that starts the stream.
+//!
+//! Only reading is supported; the p-code and the `_VBA_PROJECT` cache are
+//! ignored, so the source code returned is exactly what the VBA editor shows.
+
+use super::file::{OleError, OleFile};
+use crate::common::encoding::decode_bytes;
+use std::io::{Cursor, Read, Seek};
+
+/// Code page assumed until the `dir` stream declares one (Windows Latin 1)
+const DEFAULT_CODE_PAGE: u16 = 1252;
+
+/// Size of a decompressed chunk (2.4.1.1.3)
+const CHUNK_SIZE: usize = 4096;
+
+// dir stream record identifiers (2.3.4.2)
+const PROJECT_CODE_PAGE: u16 = 0x0003;
+const PROJECT_NAME: u16 = 0x0004;
+const PROJECT_VERSION: u16 = 0x0009;
+const REFERENCE_REGISTERED: u16 = 0x000D;
+const REFERENCE_PROJECT: u16 = 0x000E;
+const PROJECT_TERMINATOR: u16 = 0x0010;
+const REFERENCE_NAME: u16 = 0x0016;
+const MODULE_NAME: u16 = 0x0019;
+const MODULE_STREAM_NAME: u16 = 0x001A;
+const MODULE_TYPE_PROCEDURAL: u16 = 0x0021;
+const MODULE_TYPE_DOCUMENT: u16 = 0x0022;
+const MODULE_TERMINATOR: u16 = 0x002B;
+const REFERENCE_CONTROL: u16 = 0x002F;
+const MODULE_OFFSET: u16 = 0x0031;
+const MODULE_STREAM_NAME_UNICODE: u16 = 0x0032;
+const REFERENCE_ORIGINAL: u16 = 0x0033;
+const MODULE_NAME_UNICODE: u16 = 0x0047;
+
+/// A VBA project read from its compound file storage.
+#[derive(Debug, Clone, Default)]
+pub struct VbaProject {
+    name: String,
+    code_page: u16,
+    references: Vec<VbaReference>,
+    modules: Vec<VbaModule>,
+}
+
+/// Kind of a VBA module.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VbaModuleKind {
+    /// A standard (procedural) module
+    Procedural,
+    /// A document, class or designer module (e.g. `ThisDocument`, `Sheet1`)
+    Document,
+}
+
+/// A module of a VBA project with its source code.
+#[derive(Debug, Clone)]
+pub struct VbaModule {
+    name: String,
+    stream_name: String,
+    kind: VbaModuleKind,
+    source: String,
+}
+
+/// Kind of a VBA project reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VbaReferenceKind {
+    /// An Automation type library
+    Registered,
+    /// Another VBA project
+    Project,
+    /// A twiddled type library (ActiveX controls)
+    Control,
+}
+
+/// A reference from a VBA project to a type library or another project.
+#[derive(Debug, Clone)]
+pub struct VbaReference {
+    name: String,
+    kind: VbaReferenceKind,
+    libid: String,
+}
+
+impl VbaProject {
+    /// Parse a VBA project stored as a standalone compound file, such as
+    /// the `vbaProject.bin` part of a macro-enabled OOXML file.
+    pub fn parse(data: &[u8]) -> Result<Self, OleError> {
+        let mut ole = OleFile::open(Cursor::new(data))?;
+        Self::from_storage(&mut ole, &[])
+    }
+
+    /// Read the VBA project in the storage at `path` (the root for an empty path).
+    ///
+    /// For a Word document this is `["Macros"]`, for an Excel workbook
+    /// `["_VBA_PROJECT_CUR"]`.
+    pub fn from_storage<R: Read + Seek>(
+        ole: &mut OleFile<R>,
+        path: &[&str],
+    ) -> Result<Self, OleError> {
+        let mut stream_path = path.to_vec();
+        stream_path.extend(["VBA", "dir"]);
+        let dir = decompress(&ole.open_stream(&stream_path)?)?;
+        let mut project = parse_dir(&dir)?;
+
+        for module in &mut project.modules {
+            stream_path.pop();
+            stream_path.push(&module.stream_name);
+            let data = ole.open_stream(&stream_path)?;
+            let offset = module.source.len();
+            module.source.clear();
+            let compressed = data.get(offset..).ok_or_else(|| {
+                OleError::InvalidData(format!("module offset beyond stream {}", module.name))
+            })?;
+            module.source = decode(&decompress(compressed)?, project.code_page);
+        }
+
+        Ok(project)
+    }
+
+    /// Name of the project
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Code page of the project's names and source code
+    pub fn code_page(&self) -> u16 {
+        self.code_page
+    }
+
+    /// References to type libraries and other projects
+    pub fn references(&self) -> &[VbaReference] {
+        &self.references
+    }
+
+    /// Modules in the order the project lists them
+    pub fn modules(&self) -> &[VbaModule] {
+        &self.modules
+    }
+
+    /// Find a module by name (case-insensitive, like VBA itself)
+    pub fn module(&self, name: &str) -> Option<&VbaModule> {
+        self.modules
+            .iter()
+            .find(|module| module.name.eq_ignore_ascii_case(name))
+    }
+}
+
+impl VbaModule {
+    /// Name of the module
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Name of the stream holding the module in the `VBA` storage
+    pub fn stream_name(&self) -> &str {
+        &self.stream_name
+    }
+
+    /// Kind of the module
+    pub fn kind(&self) -> VbaModuleKind {
+        self.kind
+    }
+
+    /// Source code of the module, including its `Attribute` lines
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl VbaReference {
+    /// Name of the reference
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Kind of the reference
+    pub fn kind(&self) -> VbaReferenceKind {
+        self.kind
+    }
+
+    /// Library identifier, e.g.
+    /// `*\G{00020430-0000-0000-C000-000000000046}#2.0#0#...\stdole2.tlb#OLE Automation`
+    pub fn libid(&self) -> &str {
+        &self.libid
+    }
+}
+
+/// Decompress a compressed container (2.4.1).
+///
+/// # Errors
+///
+/// Returns an error if the signature byte is missing or a chunk is truncated.
+pub fn decompress(data: &[u8]) -> Result<Vec<u8>, OleError> {
+    let Some((&signature, mut rest)) = data.split_first() else {
+        return Err(OleError::InvalidData("empty compressed container".into()));
+    };
+    if signature != 0x01 {
+        return Err(OleError::InvalidData(format!(
+            "invalid compressed container signature {signature:#04x}"
+        )));
+    }
+
+    let mut out = Vec::with_capacity(data.len() * 2);
+    while rest.len() >= 2 {
+        let header = u16::from_le_bytes([rest[0], rest[1]]);
+        let size = ((header & 0x0FFF) as usize + 3).min(rest.len());
+        let chunk = &rest[2..size];
+        rest = &rest[size..];
+
+        if header & 0x8000 == 0 {
+            // Raw chunk of exactly 4096 bytes
+            out.extend_from_slice(&chunk[..chunk.len().min(CHUNK_SIZE)]);
+            continue;
+        }
+        decompress_chunk(chunk, &mut out)?;
+    }
+
+    Ok(out)
+}
+
+/// Decompress the token sequences of one compressed chunk (2.4.1.3.2)
+fn decompress_chunk(mut chunk: &[u8], out: &mut Vec<u8>) -> Result<(), OleError> {
+    let start = out.len();
+    while let Some((&flags, rest)) = chunk.split_first() {
+        chunk = rest;
+        for bit in 0..8 {
+            if chunk.is_empty() {
+                break;
+            }
+            if flags & (1 << bit) == 0 {
+                out.push(chunk[0]);
+                chunk = &chunk[1..];
+                continue;
+            }
+
+            if chunk.len() < 2 {
+                return Err(OleError::InvalidData("truncated copy token".into()));
+            }
+            let token = u16::from_le_bytes([chunk[0], chunk[1]]) as usize;
+            chunk = &chunk[2..];
+
+            // The split between offset and length depends on how much of
+            // the chunk has been decompressed so far (2.4.1.3.19.1)
+            let position = out.len() - start;
+            let bit_count = (usize::BITS - (position.max(1) - 1).leading_zeros()).max(4);
+            let length_mask = 0xFFFF >> bit_count;
+            let offset = (token >> (16 - bit_count)) + 1;
+            let length = (token & length_mask) + 3;
+            if offset > position {
+                return Err(OleError::InvalidData(
+                    "copy token before chunk start".into(),
+                ));
+            }
+            // Copies may overlap their own output, so go byte by byte
+            for _ in 0..length {
+                out.push(out[out.len() - offset]);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Parse the decompressed `dir` stream (2.3.4.2).
+///
+/// Module sources are filled in by the caller; until then each module's
+/// `source` holds as many placeholder bytes as its text offset.
+fn parse_dir(data: &[u8]) -> Result<VbaProject, OleError> {
+    let mut project = VbaProject {
+        code_page: DEFAULT_CODE_PAGE,
+        ..Default::default()
+    };
+    let mut module: Option<VbaModule> = None;
+    let mut previous = 0u16;
+    let mut pos = 0usize;
+
+    while pos + 6 <= data.len() {
+        let id = u16::from_le_bytes([data[pos], data[pos + 1]]);
+        let size = u32::from_le_bytes([data[pos + 2], data[pos + 3], data[pos + 4], data[pos + 5]])
+            as usize;
+        pos += 6;
+        // PROJECTVERSION declares a size of 4 but carries 6 bytes
+        let len = if id == PROJECT_VERSION { 6 } else { size };
+        let record = data
+            .get(pos..pos + len)
+            .ok_or_else(|| OleError::InvalidData(format!("truncated dir record {id:#06x}")))?;
+        pos += len;
+
+        let code_page = project.code_page;
+        match id {
+            PROJECT_CODE_PAGE if record.len() >= 2 => {
+                project.code_page = u16::from_le_bytes([record[0], record[1]]);
+            },
+            PROJECT_NAME => project.name = decode(record, code_page),
+            // The name inside a REFERENCECONTROL repeats the reference's name
+            REFERENCE_NAME if previous != REFERENCE_CONTROL => {
+                project.references.push(VbaReference {
+                    name: decode(record, code_page),
+                    kind: VbaReferenceKind::Registered,
+                    libid: String::new(),
+                });
+            },
+            REFERENCE_REGISTERED | REFERENCE_PROJECT | REFERENCE_CONTROL | REFERENCE_ORIGINAL => {
+                let Some(reference) = project.references.last_mut() else {
+                    previous = id;
+                    continue;
+                };
+                reference.kind = match id {
+                    REFERENCE_REGISTERED => VbaReferenceKind::Registered,
+                    REFERENCE_PROJECT => VbaReferenceKind::Project,
+                    _ => VbaReferenceKind::Control,
+                };
+                if reference.libid.is_empty() {
+                    // REFERENCEORIGINAL holds the libid itself, the others
+                    // prefix it with its size
+                    let libid = if id == REFERENCE_ORIGINAL {
+                        Some(record)
+                    } else {
+                        sized_bytes(record)
+                    };
+                    reference.libid = libid.map(|b| decode(b, code_page)).unwrap_or_default();
+                }
+            },
+            MODULE_NAME => {
+                module = Some(VbaModule {
+                    name: decode(record, code_page),
+                    stream_name: String::new(),
+                    kind: VbaModuleKind::Procedural,
+                    source: String::new(),
+                });
+            },
+            MODULE_NAME_UNICODE => {
+                if let Some(module) = module.as_mut() {
+                    module.name = decode_utf16(record);
+                }
+            },
+            MODULE_STREAM_NAME => {
+                if let Some(module) = module.as_mut() {
+                    module.stream_name = decode(record, code_page);
+                }
+            },
+            MODULE_STREAM_NAME_UNICODE => {
+                if let Some(module) = module.as_mut() {
+                    module.stream_name = decode_utf16(record);
+                }
+            },
+            MODULE_OFFSET if record.len() >= 4 => {
+                if let Some(module) = module.as_mut() {
+                    let offset = u32::from_le_bytes([record[0], record[1], record[2], record[3]]);
+                    module.source = " ".repeat(offset as usize);
+                }
+            },
+            MODULE_TYPE_PROCEDURAL | MODULE_TYPE_DOCUMENT => {
+                if let Some(module) = module.as_mut() {
+                    module.kind = if id == MODULE_TYPE_DOCUMENT {
+                        VbaModuleKind::Document
+                    } else {
+                        VbaModuleKind::Procedural
+                    };
+                }
+            },
+            MODULE_TERMINATOR => {
+                if let Some(mut module) = module.take() {
+                    if module.stream_name.is_empty() {
+                        module.stream_name = module.name.clone();
+                    }
+                    project.modules.push(module);
+                }
+            },
+            PROJECT_TERMINATOR => break,
+            _ => {},
+        }
+        previous = id;
+    }
+
+    Ok(project)
+}
+
+/// Bytes prefixed with their u32 length
+fn sized_bytes(record: &[u8]) -> Option<&[u8]> {
+    let size = u32::from_le_bytes(record.get(..4)?.try_into().ok()?) as usize;
+    record.get(4..4 + size)
+}
+
+/// Decode MBCS text in the project's code page
+fn decode(bytes: &[u8], code_page: u16) -> String {
+    decode_bytes(bytes, Some(u32::from(code_page)))
+        .unwrap_or_else(|| String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Decode UTF-16LE text
+fn decode_utf16(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ole::writer::OleWriter;
+
+    /// Compress `data` using literal tokens only, which is valid (if
+    /// useless) compression that any reader must accept.
+    fn compress_literals(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0x01];
+        for chunk in data.chunks(CHUNK_SIZE) {
+            // A full chunk of literals would outgrow the 12-bit size, so
+            // store it raw
+            if chunk.len() == CHUNK_SIZE {
+                out.extend_from_slice(&0x3FFFu16.to_le_bytes());
+                out.extend_from_slice(chunk);
+                continue;
+            }
+            let mut body = Vec::new();
+            for group in chunk.chunks(8) {
+                body.push(0x00);
+                body.extend_from_slice(group);
+            }
+            let header = 0xB000 | (body.len() as u16 + 2 - 3);
+            out.extend_from_slice(&header.to_le_bytes());
+            out.extend_from_slice(&body);
+        }
+        out
+    }
+
+    fn record(out: &mut Vec<u8>, id: u16, data: &[u8]) {
+        out.extend_from_slice(&id.to_le_bytes());
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(data);
+    }
+
+    fn sized(text: &str) -> Vec<u8> {
+        let mut out = (text.len() as u32).to_le_bytes().to_vec();
+        out.extend_from_slice(text.as_bytes());
+        out
+    }
+
+    fn utf16(text: &str) -> Vec<u8> {
+        text.encode_utf16().flat_map(u16::to_le_bytes).collect()
+    }
+
+    fn dir_stream(modules: &[(&str, u16, u32)]) -> Vec<u8> {
+        let mut dir = Vec::new();
+        record(&mut dir, 0x0001, &1u32.to_le_bytes());
+        record(&mut dir, 0x0002, &0x0409u32.to_le_bytes());
+        record(&mut dir, PROJECT_CODE_PAGE, &1252u16.to_le_bytes());
+        record(&mut dir, PROJECT_NAME, b"Project");
+        // PROJECTVERSION: reserved size of 4, then 6 bytes of version
+        dir.extend_from_slice(&PROJECT_VERSION.to_le_bytes());
+        dir.extend_from_slice(&4u32.to_le_bytes());
+        dir.extend_from_slice(&[0x6B, 0x9E, 0x7A, 0x1D, 0x04, 0x00]);
+
+        let stdole = r"*\G{00020430-0000-0000-C000-000000000046}#2.0#0#C:\Windows\System32\stdole2.tlb#OLE Automation";
+        record(&mut dir, REFERENCE_NAME, b"stdole");
+        record(&mut dir, 0x003E, &utf16("stdole"));
+        let mut registered = sized(stdole);
+        registered.extend_from_slice(&[0; 6]);
+        record(&mut dir, REFERENCE_REGISTERED, &registered);
+
+        record(&mut dir, REFERENCE_NAME, b"MSForms");
+        record(&mut dir, 0x003E, &utf16("MSForms"));
+        let mut control = sized(r"*\G{0D452EE1-E08F-101A-852E-02608C4D0BB4}#2.0#0#FM20.DLL#");
+        control.extend_from_slice(&[0; 6]);
+        record(&mut dir, REFERENCE_CONTROL, &control);
+        record(&mut dir, REFERENCE_NAME, b"MSForms");
+        record(&mut dir, 0x003E, &utf16("MSForms"));
+        let mut extended =
+            sized(r"*\G{AF2C0D8D-0000-0000-0000-000000000000}#2.0#0#C:\Temp\MSForms.exd#");
+        extended.extend_from_slice(&[0; 22]);
+        record(&mut dir, 0x0030, &extended);
+
+        record(&mut dir, 0x000F, &(modules.len() as u16).to_le_bytes());
+        record(&mut dir, 0x0013, &0xFFFFu16.to_le_bytes());
+        for &(name, kind, offset) in modules {
+            record(&mut dir, MODULE_NAME, name.as_bytes());
+            record(&mut dir, MODULE_NAME_UNICODE, &utf16(name));
+            record(&mut dir, MODULE_STREAM_NAME, name.as_bytes());
+            record(&mut dir, MODULE_STREAM_NAME_UNICODE, &utf16(name));
+            record(&mut dir, 0x001C, b"");
+            record(&mut dir, 0x0048, b"");
+            record(&mut dir, MODULE_OFFSET, &offset.to_le_bytes());
+            record(&mut dir, 0x001E, &0u32.to_le_bytes());
+            record(&mut dir, 0x002C, &0xFFFFu16.to_le_bytes());
+            record(&mut dir, kind, b"");
+            record(&mut dir, MODULE_TERMINATOR, b"");
+        }
+        record(&mut dir, PROJECT_TERMINATOR, b"");
+        dir
+    }
+
+    #[test]
+    fn test_decompress_spec_examples() {
+        // MS-OVBA 3.2.1: no compression possible
+        let compressed = [
+            0x01, 0x19, 0xB0, 0x00, 0x61, 0x62, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x00, 0x69,
+            0x6A, 0x6B, 0x6C, 0x6D, 0x6E, 0x6F, 0x70, 0x00, 0x71, 0x72, 0x73, 0x74, 0x75, 0x76,
+            0x2E,
+        ];
+        assert_eq!(decompress(&compressed).unwrap(), b"abcdefghijklmnopqrstuv.");
+
+        // MS-OVBA 3.2.2: normal compression
+        let compressed = [
+            0x01, 0x2F, 0xB0, 0x00, 0x23, 0x61, 0x61, 0x61, 0x62, 0x63, 0x64, 0x65, 0x82, 0x66,
+            0x00, 0x70, 0x61, 0x67, 0x68, 0x69, 0x6A, 0x01, 0x38, 0x08, 0x61, 0x6B, 0x6C, 0x00,
+            0x30, 0x6D, 0x6E, 0x6F, 0x70, 0x06, 0x71, 0x02, 0x70, 0x04, 0x10, 0x72, 0x73, 0x74,
+            0x75, 0x76, 0x10, 0x77, 0x78, 0x79, 0x7A, 0x00, 0x3C,
+        ];
+        assert_eq!(
+            decompress(&compressed).unwrap(),
+            b"#aaabcdefaaaaghijaaaaaklaaamnopqaaaaaaaaaaaarstuvwxyzaaa"
+        );
+
+        // MS-OVBA 3.2.3: maximum compression
+        let compressed = [0x01, 0x03, 0xB0, 0x02, 0x61, 0x45, 0x00];
+        assert_eq!(decompress(&compressed).unwrap(), [b'a'; 73]);
+
+        assert!(decompress(&[0x00, 0x01, 0x02]).is_err());
+    }
+
+    #[test]
+    fn test_parse_vba_project() {
+        let this_document = "Attribute VB_Name = \"ThisDocument\"\r\nPrivate Sub Document_Open()\r\n    MsgBox \"Hello\"\r\nEnd Sub\r\n";
+        let module1 = format!(
+            "Attribute VB_Name = \"Module1\"\r\n{}",
+            "Sub Run()\r\n    Shell \"calc.exe\"\r\nEnd Sub\r\n".repeat(200)
+        );
+        let pcode = [0xCCu8; 37];
+
+        let mut writer = OleWriter::new();
+        writer.create_storage(&["VBA"]).unwrap();
+        let dir = dir_stream(&[
+            ("ThisDocument", MODULE_TYPE_DOCUMENT, pcode.len() as u32),
+            ("Module1", MODULE_TYPE_PROCEDURAL, 0),
+        ]);
+        writer
+            .create_stream(&["VBA", "dir"], &compress_literals(&dir))
+            .unwrap();
+        let mut stream = pcode.to_vec();
+        stream.extend_from_slice(&compress_literals(this_document.as_bytes()));
+        writer
+            .create_stream(&["VBA", "ThisDocument"], &stream)
+            .unwrap();
+        writer
+            .create_stream(&["VBA", "Module1"], &compress_literals(module1.as_bytes()))
+            .unwrap();
+        writer
+            .create_stream(&["PROJECT"], b"ID=\"{}\"\r\n")
+            .unwrap();
+        let mut bin = Cursor::new(Vec::new());
+        writer.write_to(&mut bin).unwrap();
+
+        let project = VbaProject::parse(bin.get_ref()).unwrap();
+        assert_eq!(project.name(), "Project");
+        assert_eq!(project.code_page(), 1252);
+
+        let modules = project.modules();
+        assert_eq!(modules.len(), 2);
+        assert_eq!(modules[0].name(), "ThisDocument");
+        assert_eq!(modules[0].kind(), VbaModuleKind::Document);
+        assert_eq!(modules[0].source(), this_document);
+        assert_eq!(modules[1].kind(), VbaModuleKind::Procedural);
+        assert_eq!(project.module("module1").unwrap().source(), module1);
+
+        let references = project.references();
+        assert_eq!(references.len(), 2);
+        assert_eq!(references[0].name(), "stdole");
+        assert_eq!(references[0].kind(), VbaReferenceKind::Registered);
+        assert!(references[0].libid().ends_with("#OLE Automation"));
+        assert_eq!(references[1].name(), "MSForms");
+        assert_eq!(references[1].kind(), VbaReferenceKind::Control);
+        assert!(references[1].libid().contains("FM20.DLL"));
+    }
+}
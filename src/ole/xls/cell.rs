@@ -2,7 +2,7 @@
 
 use crate::ole::xls::records::{BoolErrValue, CellRecord, FormulaValue};
 use crate::ole::xls::utils;
-use crate::sheet::{Cell, CellValue};
+use crate::sheet::{Cell, CellValue, DateSystem};
 
 /// XLS cell implementation
 #[derive(Debug, Clone)]
@@ -34,6 +34,13 @@ impl XlsCell {
         }
     }
 
+    /// Read the value as a date or time, for a cell whose number format
+    /// shows one
+    pub fn into_date(mut self, date_system: DateSystem) -> Self {
+        self.value = date_system.date_cell_value(self.value);
+        self
+    }
+
     /// Create cell from BIFF record
     pub fn from_record(record: &CellRecord, sst: Option<&[String]>) -> Option<Self> {
         let (row, col, value, formula) = match record {
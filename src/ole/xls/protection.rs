@@ -1,4 +1,4 @@
-//! Sheet protection parsing for XLS BIFF8 files.
+//! Sheet and workbook protection parsing for XLS BIFF8 files.
 //!
 //! Parses the following records that define worksheet-level protection:
 //!
@@ -7,6 +7,14 @@
 //! - **SCENPROTECT** (0x00DD): Scenarios are protected.
 //! - **PASSWORD** (0x0013): Password hash for the protection.
 //!
+//! In the workbook globals, PROTECT and PASSWORD protect the workbook
+//! structure instead, and the following records are also read:
+//!
+//! - **WINDOWPROTECT** (0x0019): Window layout is protected.
+//! - **FILESHARING** (0x005B): Read-only recommendation and password to
+//!   modify.
+//! - **WRITEPROT** (0x0086): The file is write-reserved; it has no payload.
+//!
 //! # Record Formats
 //!
 //! All four records share a trivial 2-byte payload (a single `u16`):
@@ -29,6 +37,12 @@ pub const OBJECTPROTECT_TYPE: u16 = 0x0063;
 pub const SCENPROTECT_TYPE: u16 = 0x00DD;
 /// PASSWORD record type.
 pub const PASSWORD_TYPE: u16 = 0x0013;
+/// WINDOWPROTECT record type.
+pub const WINDOWPROTECT_TYPE: u16 = 0x0019;
+/// FILESHARING record type.
+pub const FILESHARING_TYPE: u16 = 0x005B;
+/// WRITEPROT record type.
+pub const WRITEPROT_TYPE: u16 = 0x0086;
 
 /// Sheet protection state parsed from BIFF8 records.
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -57,6 +71,47 @@ impl SheetProtection {
     }
 }
 
+/// Workbook protection state parsed from the BIFF8 workbook globals.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkbookProtection {
+    /// Whether the sheet structure is protected (PROTECT record).
+    pub structure_protected: bool,
+    /// Whether the window layout is protected (WINDOWPROTECT record).
+    pub windows_protected: bool,
+    /// Password hash of the structure protection (0 = no password).
+    pub password_hash: u16,
+    /// Whether the file asks to be opened read-only (FILESHARING record).
+    pub read_only_recommended: bool,
+    /// Whether a password is needed to save changes (WRITEPROT record, or a
+    /// FILESHARING record with a password hash).
+    pub write_reserved: bool,
+}
+
+impl WorkbookProtection {
+    /// Returns `true` if the structure or the window layout is locked.
+    #[inline]
+    pub fn is_protected(&self) -> bool {
+        self.structure_protected || self.windows_protected
+    }
+}
+
+/// Parse a FILESHARING record.
+///
+/// Returns the read-only recommendation flag and the hash of the password
+/// to modify (0 = no password).
+pub fn parse_file_sharing(data: &[u8]) -> XlsResult<(bool, u16)> {
+    if data.len() < 4 {
+        return Err(XlsError::InvalidLength {
+            expected: 4,
+            found: data.len(),
+        });
+    }
+    Ok((
+        binary::read_u16_le_at(data, 0)? != 0,
+        binary::read_u16_le_at(data, 2)?,
+    ))
+}
+
 /// Parse a boolean protection record (PROTECT, OBJECTPROTECT, SCENPROTECT).
 ///
 /// Returns `true` if the 2-byte payload is non-zero.
@@ -111,6 +166,13 @@ mod tests {
         assert_eq!(parse_password(&data).unwrap(), 0);
     }
 
+    #[test]
+    fn test_parse_file_sharing() {
+        let data = [1, 0, 0x4B, 0xCE, 0, 0];
+        assert_eq!(parse_file_sharing(&data).unwrap(), (true, 0xCE4B));
+        assert!(parse_file_sharing(&data[..2]).is_err());
+    }
+
     #[test]
     fn test_sheet_protection_default() {
         let prot = SheetProtection::default();
@@ -470,7 +470,6 @@ impl SharedStringTable {
     }
 
     /// Parse a single string entry from SST data
-    #[allow(dead_code)]
     fn parse_string_entry(data: &[u8], encoding: &XlsEncoding) -> XlsResult<(String, usize)> {
         if data.len() < 3 {
             return Err(XlsError::InvalidLength {
@@ -550,6 +549,15 @@ impl SharedStringTable {
             string_data = &data[offset..offset + cch];
             string_consumed = cch;
 
+            // Compressed UTF-16 keeps only the low byte of each character,
+            // whatever the workbook codepage says
+            if matches!(encoding, XlsEncoding::Utf16Le) {
+                return Ok((
+                    string_data.iter().map(|&b| b as char).collect(),
+                    consumed + string_consumed,
+                ));
+            }
+
             // Convert using the specified encoding
             match encoding.decode(string_data) {
                 Ok(s) => Ok((s, consumed + string_consumed)),
@@ -559,6 +567,32 @@ impl SharedStringTable {
     }
 }
 
+/// FORMAT record - number format string
+#[derive(Debug, Clone)]
+pub struct FormatRecord {
+    pub format_index: u16,
+    pub format: String,
+}
+
+impl FormatRecord {
+    pub fn parse(data: &[u8], encoding: &XlsEncoding) -> XlsResult<Self> {
+        if data.len() < 5 {
+            return Err(XlsError::InvalidLength {
+                expected: 5,
+                found: data.len(),
+            });
+        }
+
+        let format_index = binary::read_u16_le_at(data, 0)?;
+        let (format, _) = SharedStringTable::parse_string_entry(&data[2..], encoding)?;
+
+        Ok(FormatRecord {
+            format_index,
+            format,
+        })
+    }
+}
+
 /// XF (Extended Format) record - cell formatting
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -567,7 +601,6 @@ pub struct ExtendedFormat {
     pub format_index: u16,
 }
 
-#[allow(dead_code)]
 impl ExtendedFormat {
     pub fn parse(data: &[u8]) -> XlsResult<Self> {
         if data.len() < 4 {
@@ -674,6 +707,18 @@ impl CellRecord {
         }
     }
 
+    pub fn xf_index(&self) -> u16 {
+        match self {
+            CellRecord::Blank { xf_index, .. } => *xf_index,
+            CellRecord::Number { xf_index, .. } => *xf_index,
+            CellRecord::Label { xf_index, .. } => *xf_index,
+            CellRecord::BoolErr { xf_index, .. } => *xf_index,
+            CellRecord::Rk { xf_index, .. } => *xf_index,
+            CellRecord::LabelSst { xf_index, .. } => *xf_index,
+            CellRecord::Formula { xf_index, .. } => *xf_index,
+        }
+    }
+
     pub fn parse(record_type: u16, data: &[u8], encoding: &XlsEncoding) -> XlsResult<Self> {
         match record_type {
             0x0201 => Self::parse_blank(data),           // Blank
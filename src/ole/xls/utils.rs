@@ -319,21 +319,7 @@ pub fn parse_cell_reference(ref_str: &str) -> Option<(u32, u32)> {
 /// Convert serial date to datetime
 #[allow(dead_code)]
 pub fn excel_date_to_datetime(serial: f64, is_1904: bool) -> Option<chrono::NaiveDateTime> {
-    use chrono::{Duration, NaiveDate};
-
-    let base_date = if is_1904 {
-        NaiveDate::from_ymd_opt(1904, 1, 1)?
-    } else {
-        NaiveDate::from_ymd_opt(1899, 12, 30)?
-    };
-
-    let days = serial.trunc() as i64;
-    let seconds = ((serial.fract() * 86400.0).round() as i64) * 1_000_000; // microseconds
-
-    let date = base_date + Duration::days(days);
-    let time = Duration::microseconds(seconds);
-
-    Some(date.and_time(chrono::NaiveTime::from_hms_opt(0, 0, 0)?) + time)
+    crate::sheet::DateSystem::from_1904_flag(is_1904).serial_to_datetime(serial)
 }
 
 #[cfg(test)]
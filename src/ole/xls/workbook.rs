@@ -1,16 +1,19 @@
 //! Workbook implementation for XLS files
 
+use crate::common::{Protection, ProtectionKind, trace};
 use crate::ole::file::OleFile;
 use crate::ole::xls::cell::XlsCell;
 use crate::ole::xls::error::{XlsError, XlsResult};
 use crate::ole::xls::pivot_table::PivotTable;
 use crate::ole::xls::records::{
-    BiffVersion, BofRecord, BoundSheetRecord, CellRecord, DimensionsRecord, RecordIter,
-    SharedStringTable, XlsEncoding,
+    BiffVersion, BofRecord, BoundSheetRecord, CellRecord, DimensionsRecord, ExtendedFormat,
+    FormatRecord, RecordIter, SharedStringTable, XlsEncoding,
 };
 use crate::ole::xls::worksheet::XlsWorksheet;
 use crate::ole::xls::{autofilter, comments, hyperlinks, merged_cells, pivot_table, protection};
-use crate::sheet::{Result, Worksheet as SheetTrait, WorksheetIterator};
+use crate::sheet::date_system::is_date_format_id;
+use crate::sheet::{DateSystem, Result, Worksheet as SheetTrait, WorksheetIterator};
+use std::collections::HashMap;
 use std::io::{Read, Seek};
 use std::sync::Arc;
 
@@ -24,6 +27,12 @@ pub struct XlsWorkbook<R: Read + Seek> {
     shared_strings: Option<Arc<Vec<String>>>,
     biff_version: BiffVersion,
     is_1904_date_system: bool,
+    /// Whether each XF record shows numbers as dates or times, by XF index
+    date_xfs: Vec<bool>,
+    /// The `_VBA_PROJECT_CUR` storage copied into a standalone compound file
+    vba_project_data: Option<Vec<u8>>,
+    /// Workbook-level protection from the globals substream
+    protection: protection::WorkbookProtection,
 }
 
 impl<R: Read + Seek> XlsWorkbook<R> {
@@ -38,6 +47,9 @@ impl<R: Read + Seek> XlsWorkbook<R> {
             shared_strings: None,
             biff_version: BiffVersion::Biff8,
             is_1904_date_system: false,
+            date_xfs: Vec::new(),
+            vba_project_data: None,
+            protection: protection::WorkbookProtection::default(),
         };
 
         workbook.parse_workbook()?;
@@ -60,6 +72,9 @@ impl<R: Read + Seek> XlsWorkbook<R> {
             shared_strings: None,
             biff_version: BiffVersion::Biff8,
             is_1904_date_system: false,
+            date_xfs: Vec::new(),
+            vba_project_data: None,
+            protection: protection::WorkbookProtection::default(),
         };
 
         workbook.parse_workbook()?;
@@ -87,6 +102,12 @@ impl<R: Read + Seek> XlsWorkbook<R> {
             &mut strings,
         )?;
 
+        // Copy the VBA project out of the _VBA_PROJECT_CUR storage
+        if self.ole_file.directory_exists(&["_VBA_PROJECT_CUR"]) {
+            self.vba_project_data =
+                crate::ole::object::extract_storage(&mut self.ole_file, &["_VBA_PROJECT_CUR"]).ok();
+        }
+
         // Use Arc for zero-copy sharing across worksheets
         self.shared_strings = Some(Arc::new(strings));
         self.worksheet_names = bound_sheets.iter().map(|s| s.name.clone()).collect();
@@ -97,8 +118,13 @@ impl<R: Read + Seek> XlsWorkbook<R> {
                 Ok(worksheet) => {
                     self.worksheets.push(worksheet);
                 },
-                Err(_e) => {
+                Err(_err) => {
                     // Failed to parse worksheet, continue with next
+                    trace::warn!(
+                        sheet = %bound_sheet.name,
+                        error = %_err,
+                        "skipping worksheet that failed to parse"
+                    );
                 },
             }
         }
@@ -120,6 +146,9 @@ impl<R: Read + Seek> XlsWorkbook<R> {
             records.push(record_result?);
         }
 
+        let mut number_formats = HashMap::new();
+        let mut xf_formats = Vec::new();
+
         let mut i = 0;
         while i < records.len() {
             let record = &records[i];
@@ -145,6 +174,18 @@ impl<R: Read + Seek> XlsWorkbook<R> {
                         self.is_1904_date_system = flag == 1;
                     }
                 },
+                0x041E => {
+                    // Format
+                    if let Ok(format) = FormatRecord::parse(&record.data, encoding) {
+                        number_formats.insert(format.format_index, format.format);
+                    }
+                },
+                0x00E0 => {
+                    // XF, numbered in record order; keep the numbering even
+                    // if a record is damaged
+                    xf_formats
+                        .push(ExtendedFormat::parse(&record.data).map_or(0, |xf| xf.format_index));
+                },
                 0x0085 => {
                     // BoundSheet8
                     let sheet = BoundSheetRecord::parse(&record.data, encoding)?;
@@ -168,6 +209,31 @@ impl<R: Read + Seek> XlsWorkbook<R> {
                     // Skip the CONTINUE records we consumed
                     i = sst_idx - 1;
                 },
+                // --- Workbook protection records ---
+                rt if rt == protection::PROTECT_TYPE => {
+                    if let Ok(val) = protection::parse_protect_bool(&record.data) {
+                        self.protection.structure_protected = val;
+                    }
+                },
+                rt if rt == protection::WINDOWPROTECT_TYPE => {
+                    if let Ok(val) = protection::parse_protect_bool(&record.data) {
+                        self.protection.windows_protected = val;
+                    }
+                },
+                rt if rt == protection::PASSWORD_TYPE => {
+                    if let Ok(hash) = protection::parse_password(&record.data) {
+                        self.protection.password_hash = hash;
+                    }
+                },
+                rt if rt == protection::FILESHARING_TYPE => {
+                    if let Ok((read_only, hash)) = protection::parse_file_sharing(&record.data) {
+                        self.protection.read_only_recommended = read_only;
+                        self.protection.write_reserved |= hash != 0;
+                    }
+                },
+                rt if rt == protection::WRITEPROT_TYPE => {
+                    self.protection.write_reserved = true;
+                },
                 0x000A => {
                     // EOF - End of workbook globals
                     break;
@@ -179,6 +245,14 @@ impl<R: Read + Seek> XlsWorkbook<R> {
             i += 1;
         }
 
+        self.date_xfs = xf_formats
+            .into_iter()
+            .map(|format_index| {
+                let code = number_formats.get(&format_index).map(String::as_str);
+                is_date_format_id(u32::from(format_index), code)
+            })
+            .collect();
+
         Ok(())
     }
 
@@ -189,6 +263,13 @@ impl<R: Read + Seek> XlsWorkbook<R> {
         encoding: &XlsEncoding,
         record_iter: &mut RecordIter<Reader>,
     ) -> XlsResult<XlsWorksheet> {
+        let _span = trace::info_span!(
+            "parse_worksheet",
+            name = %bound_sheet.name,
+            offset = bound_sheet.position
+        )
+        .entered();
+
         // Seek to the worksheet position
         record_iter.seek(bound_sheet.position as u64)?;
 
@@ -211,15 +292,27 @@ impl<R: Read + Seek> XlsWorkbook<R> {
             .shared_strings
             .clone()
             .unwrap_or_else(|| Arc::new(Vec::new()));
-        Self::parse_worksheet_records(record_iter, encoding, &bound_sheet.name, shared_strings)
+        let date_system = DateSystem::from_1904_flag(self.is_1904_date_system);
+        Self::parse_worksheet_records(
+            record_iter,
+            encoding,
+            &bound_sheet.name,
+            shared_strings,
+            &self.date_xfs,
+            date_system,
+        )
     }
 
     /// Parse worksheet records sequentially
+    ///
+    /// `date_xfs` holds whether each XF shows numbers as dates.
     fn parse_worksheet_records<Reader: Read + Seek>(
         record_iter: &mut RecordIter<Reader>,
         encoding: &XlsEncoding,
         name: &str,
         shared_strings: Arc<Vec<String>>,
+        date_xfs: &[bool],
+        date_system: DateSystem,
     ) -> XlsResult<XlsWorksheet> {
         let mut worksheet = XlsWorksheet::with_shared_strings(name.to_string(), shared_strings);
 
@@ -261,7 +354,10 @@ impl<R: Read + Seek> XlsWorkbook<R> {
                 0x0006   // Formula
                 => {
                     let cell_record = CellRecord::parse(record.header.record_type, &record.data, encoding)?;
-                    if let Some(cell) = XlsCell::from_record(&cell_record, worksheet.shared_strings()) {
+                    if let Some(mut cell) = XlsCell::from_record(&cell_record, worksheet.shared_strings()) {
+                        if date_xfs.get(usize::from(cell_record.xf_index())) == Some(&true) {
+                            cell = cell.into_date(date_system);
+                        }
                         worksheet.add_cell(cell);
                     }
                 }
@@ -414,6 +510,51 @@ impl<R: Read + Seek> XlsWorkbook<R> {
             .get(index)
             .ok_or_else(|| XlsError::WorksheetNotFound(format!("Sheet index {}", index)))
     }
+
+    /// Get the workbook-level protection read from the workbook globals.
+    pub fn workbook_protection(&self) -> &protection::WorkbookProtection {
+        &self.protection
+    }
+
+    /// Get the editing protection of the workbook.
+    ///
+    /// Combines the workbook-level protection with the protection of every
+    /// worksheet. A locked structure, window layout or sheet, or a password
+    /// to modify, makes the workbook read-only.
+    pub fn protection(&self) -> Protection {
+        let workbook = &self.protection;
+        let mut result = Protection::default();
+        if workbook.read_only_recommended {
+            result = result.or(Protection::new(ProtectionKind::ReadOnlyRecommended));
+        }
+        if workbook.write_reserved {
+            result = result.or(Protection::with_password(ProtectionKind::ReadOnly));
+        }
+        if workbook.is_protected() {
+            result = result.or(Protection {
+                kind: ProtectionKind::ReadOnly,
+                has_password: workbook.password_hash != 0,
+            });
+        }
+        for sheet in self.worksheets.iter().map(XlsWorksheet::protection) {
+            if sheet.is_protected() {
+                result = result.or(Protection {
+                    kind: ProtectionKind::ReadOnly,
+                    has_password: sheet.has_password(),
+                });
+            }
+        }
+        result
+    }
+
+    /// Get the VBA project of the workbook, if it has macros.
+    ///
+    /// The `_VBA_PROJECT_CUR` storage is returned as a standalone compound
+    /// file, which [`VbaProject::parse`](crate::ole::vba::VbaProject::parse)
+    /// reads.
+    pub fn vba_project_data(&self) -> Option<&[u8]> {
+        self.vba_project_data.as_deref()
+    }
 }
 
 impl<R: Read + Seek + std::fmt::Debug + Send + Sync> crate::sheet::WorkbookTrait
@@ -441,13 +582,16 @@ impl<R: Read + Seek + std::fmt::Debug + Send + Sync> crate::sheet::WorkbookTrait
                 return Ok(Box::new(worksheet));
             }
         }
-        Err(Box::new(XlsError::WorksheetNotFound(name.to_string())))
+        Err(Box::new(crate::common::Error::InvalidReference(format!(
+            "Worksheet '{}' not found",
+            name
+        ))))
     }
 
     fn worksheet_by_index(&self, index: usize) -> Result<Box<dyn SheetTrait + '_>> {
         if index >= self.worksheets.len() {
-            return Err(Box::new(XlsError::WorksheetNotFound(format!(
-                "Index {} out of bounds",
+            return Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
                 index
             ))));
         }
@@ -493,3 +637,39 @@ impl<'a> WorksheetIterator<'a> for XlsWorksheetIterator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::XlsWorkbook;
+    use crate::ole::xls::writer::{CellStyle, XlsWriter};
+    use crate::sheet::{CellValue, WorkbookTrait, Worksheet};
+    use std::io::Cursor;
+
+    #[test]
+    fn date_cells_use_1904_date_system() {
+        let mut writer = XlsWriter::new();
+        writer.set_1904_dates(true);
+        let sheet = writer.add_worksheet("Dates").unwrap();
+        let date_style = writer.add_cell_style(CellStyle {
+            number_format: Some("yyyy-mm-dd".into()),
+            ..Default::default()
+        });
+        writer
+            .write_number_with_format(sheet, 0, 0, 1000.0, date_style)
+            .unwrap();
+        writer.write_number(sheet, 1, 0, 1000.0).unwrap();
+        let mut data = Cursor::new(Vec::new());
+        writer.write_to(&mut data).unwrap();
+
+        let workbook = XlsWorkbook::new(Cursor::new(data.into_inner())).unwrap();
+        assert!(workbook.is_1904_date_system());
+        let sheet = workbook.xls_worksheet(0).unwrap();
+        let date = sheet.cell_value(0, 0).unwrap();
+        assert_eq!(*date, CellValue::DateTime(1000.0));
+        assert_eq!(
+            date.as_datetime(true).unwrap().to_string(),
+            "1906-09-27 00:00:00"
+        );
+        assert_eq!(*sheet.cell_value(1, 0).unwrap(), CellValue::Float(1000.0));
+    }
+}
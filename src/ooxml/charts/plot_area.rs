@@ -43,6 +43,20 @@ impl PlotArea {
         self.axes.push(axis);
         self
     }
+
+    /// Iterate over the series of all type groups, in plot order.
+    pub fn series(&self) -> impl Iterator<Item = &Series> {
+        self.type_groups
+            .iter()
+            .flat_map(|g| g.common().series.iter())
+    }
+
+    /// Mutably iterate over the series of all type groups, in plot order.
+    pub fn series_mut(&mut self) -> impl Iterator<Item = &mut Series> {
+        self.type_groups
+            .iter_mut()
+            .flat_map(|g| g.common_mut().series.iter_mut())
+    }
 }
 
 impl Default for PlotArea {
@@ -87,6 +101,50 @@ pub enum TypeGroup {
     Surface3D(Surface3DTypeGroup),
 }
 
+impl TypeGroup {
+    /// Get the properties shared by all type groups.
+    pub fn common(&self) -> &TypeGroupCommon {
+        match self {
+            Self::Area(g) => &g.common,
+            Self::Area3D(g) => &g.common,
+            Self::Bar(g) => &g.common,
+            Self::Bar3D(g) => &g.common,
+            Self::Bubble(g) => &g.common,
+            Self::Doughnut(g) => &g.common,
+            Self::Line(g) => &g.common,
+            Self::Line3D(g) => &g.common,
+            Self::Pie(g) => &g.common,
+            Self::Pie3D(g) => &g.common,
+            Self::Radar(g) => &g.common,
+            Self::Scatter(g) => &g.common,
+            Self::Stock(g) => &g.common,
+            Self::Surface(g) => &g.common,
+            Self::Surface3D(g) => &g.common,
+        }
+    }
+
+    /// Get the properties shared by all type groups, mutably.
+    pub fn common_mut(&mut self) -> &mut TypeGroupCommon {
+        match self {
+            Self::Area(g) => &mut g.common,
+            Self::Area3D(g) => &mut g.common,
+            Self::Bar(g) => &mut g.common,
+            Self::Bar3D(g) => &mut g.common,
+            Self::Bubble(g) => &mut g.common,
+            Self::Doughnut(g) => &mut g.common,
+            Self::Line(g) => &mut g.common,
+            Self::Line3D(g) => &mut g.common,
+            Self::Pie(g) => &mut g.common,
+            Self::Pie3D(g) => &mut g.common,
+            Self::Radar(g) => &mut g.common,
+            Self::Scatter(g) => &mut g.common,
+            Self::Stock(g) => &mut g.common,
+            Self::Surface(g) => &mut g.common,
+            Self::Surface3D(g) => &mut g.common,
+        }
+    }
+}
+
 /// Common properties for type groups.
 #[derive(Debug, Clone)]
 pub struct TypeGroupCommon {
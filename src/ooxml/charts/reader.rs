@@ -6,7 +6,7 @@
 use crate::ooxml::charts::axis::{Axis, CategoryAxis, DateAxis, SeriesAxis, ValueAxis};
 use crate::ooxml::charts::chart::{Chart, View3D, WallFloor};
 use crate::ooxml::charts::legend::Legend;
-use crate::ooxml::charts::models::{NumericData, RichText, StringData, TitleText};
+use crate::ooxml::charts::models::{DataSourceRef, NumericData, RichText, StringData, TitleText};
 use crate::ooxml::charts::plot_area::{
     AreaTypeGroup, Bar3DTypeGroup, BarTypeGroup, LineTypeGroup, PieTypeGroup, PlotArea,
     ScatterTypeGroup, TypeGroup, TypeGroupCommon,
@@ -16,63 +16,67 @@ use crate::ooxml::charts::types::{
     AxisPosition, BarDirection, BarGrouping, DisplayBlanks, LegendPosition, ScatterStyle,
 };
 use crate::ooxml::error::{OoxmlError, Result};
+use crate::ooxml::opc::constants::relationship_type as rt;
+use crate::ooxml::opc::{OpcPackage, Part};
 use quick_xml::Reader;
-use quick_xml::events::{BytesStart, Event};
+use quick_xml::events::{BytesRef, BytesStart, Event};
 use std::io::BufRead;
 
 /// Parse a chart XML document.
 pub fn parse_chart<R: BufRead>(reader: R) -> Result<Chart> {
     let mut xml_reader = Reader::from_reader(reader);
-    xml_reader.config_mut().trim_text(true);
 
     let mut chart = Chart::new();
     let mut buf = Vec::new();
 
     loop {
         match xml_reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+            Ok(Event::Start(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:chart" => {
-                        parse_chart_element(&mut xml_reader, &mut chart)?;
-                    },
-                    b"c:title" => {
+                    b"title" => {
                         chart.title = Some(parse_title(&mut xml_reader)?);
                     },
-                    b"c:autoTitleDeleted" => {
-                        chart.auto_title_deleted = parse_bool_attr(e)?;
-                    },
-                    b"c:view3D" => {
+                    b"view3D" => {
                         chart.view_3d = Some(parse_view_3d(&mut xml_reader)?);
                     },
-                    b"c:floor" => {
+                    b"floor" => {
                         chart.floor = Some(parse_wall_floor(&mut xml_reader)?);
                     },
-                    b"c:backWall" => {
+                    b"backWall" => {
                         chart.back_wall = Some(parse_wall_floor(&mut xml_reader)?);
                     },
-                    b"c:sideWall" => {
+                    b"sideWall" => {
                         chart.side_wall = Some(parse_wall_floor(&mut xml_reader)?);
                     },
-                    b"c:plotArea" => {
+                    b"plotArea" => {
                         chart.plot_area = parse_plot_area(&mut xml_reader)?;
                     },
-                    b"c:legend" => {
+                    b"legend" => {
                         chart.legend = Some(parse_legend(&mut xml_reader)?);
                     },
-                    b"c:plotVisOnly" => {
+                    _ => {},
+                }
+            },
+            Ok(Event::Empty(ref e)) => {
+                let tag_name = e.local_name();
+                match tag_name.as_ref() {
+                    b"autoTitleDeleted" => {
+                        chart.auto_title_deleted = parse_bool_attr(e)?;
+                    },
+                    b"plotVisOnly" => {
                         chart.plot_visible_only = parse_bool_attr(e)?;
                     },
-                    b"c:dispBlanksAs" => {
+                    b"dispBlanksAs" => {
                         chart.display_blanks_as = parse_display_blanks(e)?;
                     },
-                    b"c:date1904" => {
+                    b"date1904" => {
                         chart.date_1904 = parse_bool_attr(e)?;
                     },
-                    b"c:roundedCorners" => {
+                    b"roundedCorners" => {
                         chart.rounded_corners = parse_bool_attr(e)?;
                     },
-                    b"c:style" => {
+                    b"style" => {
                         chart.style = parse_u32_attr(e, b"val");
                     },
                     _ => {},
@@ -88,39 +92,75 @@ pub fn parse_chart<R: BufRead>(reader: R) -> Result<Chart> {
     Ok(chart)
 }
 
-fn parse_chart_element<R: BufRead>(reader: &mut Reader<R>, _chart: &mut Chart) -> Result<()> {
-    let mut buf = Vec::new();
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:chart" => break,
-            Ok(Event::Eof) => break,
-            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
-            _ => {},
-        }
-        buf.clear();
-    }
-    Ok(())
+/// Parse every chart part referenced from `part`.
+///
+/// Charts are returned in relationship ID order (`rId1`, `rId2`, ...), which
+/// is the order Office assigns them as they are inserted.
+pub fn read_related_charts(package: &OpcPackage, part: &dyn Part) -> Result<Vec<Chart>> {
+    let mut rels: Vec<_> = part
+        .rels()
+        .iter()
+        .filter(|rel| rel.reltype() == rt::CHART && !rel.is_external())
+        .collect();
+    rels.sort_by(|a, b| (a.r_id().len(), a.r_id()).cmp(&(b.r_id().len(), b.r_id())));
+
+    rels.into_iter()
+        .map(|rel| {
+            let chart_part = package.get_part(&rel.target_partname()?)?;
+            parse_chart(chart_part.try_blob()?)
+        })
+        .collect()
 }
 
 fn parse_title<R: BufRead>(reader: &mut Reader<R>) -> Result<TitleText> {
     let mut text = String::new();
+    let mut formula: Option<String> = None;
     let mut buf = Vec::new();
     let mut in_text = false;
+    let mut in_formula = false;
+    let mut in_rich = false;
+    let mut paragraphs = 0;
 
     loop {
         match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"a:t" => {
-                in_text = true;
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                // Rich text runs and cached reference values both carry the title text.
+                b"t" | b"v" => in_text = true,
+                b"f" => {
+                    in_formula = true;
+                    formula = Some(String::new());
+                },
+                b"rich" => in_rich = true,
+                b"p" if in_rich => {
+                    if paragraphs > 0 {
+                        text.push('\n');
+                    }
+                    paragraphs += 1;
+                },
+                _ => {},
+            },
+            Ok(Event::Text(ref e)) if in_text || in_formula => {
+                let decoded = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                if in_formula {
+                    formula.get_or_insert_default().push_str(&decoded);
+                } else {
+                    text.push_str(&decoded);
+                }
             },
-            Ok(Event::Text(e)) if in_text => {
-                text.push_str(
-                    std::str::from_utf8(e.as_ref()).map_err(|e| OoxmlError::Xml(e.to_string()))?,
-                );
+            Ok(Event::GeneralRef(ref e)) if in_text || in_formula => {
+                if in_formula {
+                    push_general_ref(formula.get_or_insert_default(), e)?;
+                } else {
+                    push_general_ref(&mut text, e)?;
+                }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"a:t" => {
-                in_text = false;
+            Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                b"t" | b"v" => in_text = false,
+                b"f" => in_formula = false,
+                b"rich" => in_rich = false,
+                b"title" => break,
+                _ => {},
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:title" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -128,7 +168,10 @@ fn parse_title<R: BufRead>(reader: &mut Reader<R>) -> Result<TitleText> {
         buf.clear();
     }
 
-    Ok(TitleText::Literal(RichText::new(text)))
+    match formula {
+        Some(formula) if text.is_empty() => Ok(TitleText::from_ref(formula)),
+        _ => Ok(TitleText::Literal(RichText::new(text))),
+    }
 }
 
 fn parse_view_3d<R: BufRead>(reader: &mut Reader<R>) -> Result<View3D> {
@@ -140,16 +183,16 @@ fn parse_view_3d<R: BufRead>(reader: &mut Reader<R>) -> Result<View3D> {
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:rotX" => view.rot_x = parse_u32_attr(e, b"val"),
-                    b"c:rotY" => view.rot_y = parse_u32_attr(e, b"val"),
-                    b"c:perspective" => view.perspective = parse_u32_attr(e, b"val"),
-                    b"c:hPercent" => view.height_percent = parse_u32_attr(e, b"val"),
-                    b"c:depthPercent" => view.depth_percent = parse_u32_attr(e, b"val"),
-                    b"c:rAngAx" => view.right_angle_axes = parse_bool_attr(e)?,
+                    b"rotX" => view.rot_x = parse_u32_attr(e, b"val"),
+                    b"rotY" => view.rot_y = parse_u32_attr(e, b"val"),
+                    b"perspective" => view.perspective = parse_u32_attr(e, b"val"),
+                    b"hPercent" => view.height_percent = parse_u32_attr(e, b"val"),
+                    b"depthPercent" => view.depth_percent = parse_u32_attr(e, b"val"),
+                    b"rAngAx" => view.right_angle_axes = parse_bool_attr(e)?,
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:view3D" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"view3D" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -167,15 +210,15 @@ fn parse_wall_floor<R: BufRead>(reader: &mut Reader<R>) -> Result<WallFloor> {
     loop {
         match reader.read_event_into(&mut buf) {
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
-                if e.local_name().as_ref() == b"c:thickness" {
+                if e.local_name().as_ref() == b"thickness" {
                     wall_floor.thickness = parse_u32_attr(e, b"val");
                 }
             },
             Ok(Event::End(ref e)) => {
                 let tag_name = e.local_name();
-                if tag_name.as_ref() == b"c:floor"
-                    || tag_name.as_ref() == b"c:backWall"
-                    || tag_name.as_ref() == b"c:sideWall"
+                if tag_name.as_ref() == b"floor"
+                    || tag_name.as_ref() == b"backWall"
+                    || tag_name.as_ref() == b"sideWall"
                 {
                     break;
                 }
@@ -199,52 +242,52 @@ fn parse_plot_area<R: BufRead>(reader: &mut Reader<R>) -> Result<PlotArea> {
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:barChart" => {
+                    b"barChart" => {
                         if let Some(group) = parse_bar_chart(reader)? {
                             plot_area.type_groups.push(TypeGroup::Bar(group));
                         }
                     },
-                    b"c:bar3DChart" => {
+                    b"bar3DChart" => {
                         if let Some(group) = parse_bar_3d_chart(reader)? {
                             plot_area.type_groups.push(TypeGroup::Bar3D(group));
                         }
                     },
-                    b"c:lineChart" => {
+                    b"lineChart" => {
                         if let Some(group) = parse_line_chart(reader)? {
                             plot_area.type_groups.push(TypeGroup::Line(group));
                         }
                     },
-                    b"c:pieChart" => {
+                    b"pieChart" => {
                         if let Some(group) = parse_pie_chart(reader)? {
                             plot_area.type_groups.push(TypeGroup::Pie(group));
                         }
                     },
-                    b"c:areaChart" => {
+                    b"areaChart" => {
                         if let Some(group) = parse_area_chart(reader)? {
                             plot_area.type_groups.push(TypeGroup::Area(group));
                         }
                     },
-                    b"c:scatterChart" => {
+                    b"scatterChart" => {
                         if let Some(group) = parse_scatter_chart(reader)? {
                             plot_area.type_groups.push(TypeGroup::Scatter(group));
                         }
                     },
-                    b"c:catAx" => {
+                    b"catAx" => {
                         if let Some(axis) = parse_category_axis(reader)? {
                             plot_area.axes.push(Axis::Category(axis));
                         }
                     },
-                    b"c:valAx" => {
+                    b"valAx" => {
                         if let Some(axis) = parse_value_axis(reader)? {
                             plot_area.axes.push(Axis::Value(axis));
                         }
                     },
-                    b"c:dateAx" => {
+                    b"dateAx" => {
                         if let Some(axis) = parse_date_axis(reader)? {
                             plot_area.axes.push(Axis::Date(axis));
                         }
                     },
-                    b"c:serAx" => {
+                    b"serAx" => {
                         if let Some(axis) = parse_series_axis(reader)? {
                             plot_area.axes.push(Axis::Series(axis));
                         }
@@ -252,7 +295,7 @@ fn parse_plot_area<R: BufRead>(reader: &mut Reader<R>) -> Result<PlotArea> {
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:plotArea" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"plotArea" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -274,7 +317,7 @@ fn parse_bar_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<BarTypeG
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:barDir" => {
+                    b"barDir" => {
                         if let Some(val) = get_attr(e, b"val") {
                             direction = if val.as_slice() == b"bar" {
                                 BarDirection::Bar
@@ -283,13 +326,13 @@ fn parse_bar_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<BarTypeG
                             };
                         }
                     },
-                    b"c:grouping" => {
+                    b"grouping" => {
                         grouping = parse_grouping(e);
                     },
-                    b"c:varyColors" => {
+                    b"varyColors" => {
                         common.vary_colors = parse_bool_attr(e).unwrap_or(false);
                     },
-                    b"c:ser" => {
+                    b"ser" => {
                         if let Some(series) = parse_series(reader)? {
                             common.series.push(series);
                         }
@@ -297,7 +340,7 @@ fn parse_bar_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<BarTypeG
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:barChart" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"barChart" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -321,7 +364,7 @@ fn parse_bar_3d_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Bar3D
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:barDir" => {
+                    b"barDir" => {
                         if let Some(val) = get_attr(e, b"val") {
                             direction = if val.as_slice() == b"bar" {
                                 BarDirection::Bar
@@ -330,13 +373,13 @@ fn parse_bar_3d_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Bar3D
                             };
                         }
                     },
-                    b"c:grouping" => {
+                    b"grouping" => {
                         grouping = parse_grouping(e);
                     },
-                    b"c:varyColors" => {
+                    b"varyColors" => {
                         common.vary_colors = parse_bool_attr(e).unwrap_or(false);
                     },
-                    b"c:ser" => {
+                    b"ser" => {
                         if let Some(series) = parse_series(reader)? {
                             common.series.push(series);
                         }
@@ -344,7 +387,7 @@ fn parse_bar_3d_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Bar3D
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:bar3DChart" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"bar3DChart" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -367,13 +410,13 @@ fn parse_line_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<LineTyp
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:grouping" => {
+                    b"grouping" => {
                         grouping = parse_grouping(e);
                     },
-                    b"c:varyColors" => {
+                    b"varyColors" => {
                         common.vary_colors = parse_bool_attr(e).unwrap_or(false);
                     },
-                    b"c:ser" => {
+                    b"ser" => {
                         if let Some(series) = parse_series(reader)? {
                             common.series.push(series);
                         }
@@ -381,7 +424,7 @@ fn parse_line_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<LineTyp
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:lineChart" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"lineChart" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -403,10 +446,10 @@ fn parse_pie_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<PieTypeG
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:varyColors" => {
+                    b"varyColors" => {
                         common.vary_colors = parse_bool_attr(e).unwrap_or(true);
                     },
-                    b"c:ser" => {
+                    b"ser" => {
                         if let Some(series) = parse_series(reader)? {
                             common.series.push(series);
                         }
@@ -414,7 +457,7 @@ fn parse_pie_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<PieTypeG
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:pieChart" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"pieChart" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -437,13 +480,13 @@ fn parse_area_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<AreaTyp
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:grouping" => {
+                    b"grouping" => {
                         grouping = parse_grouping(e);
                     },
-                    b"c:varyColors" => {
+                    b"varyColors" => {
                         common.vary_colors = parse_bool_attr(e).unwrap_or(false);
                     },
-                    b"c:ser" => {
+                    b"ser" => {
                         if let Some(series) = parse_series(reader)? {
                             common.series.push(series);
                         }
@@ -451,7 +494,7 @@ fn parse_area_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<AreaTyp
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:areaChart" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"areaChart" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -474,7 +517,7 @@ fn parse_scatter_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Scat
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:scatterStyle" => {
+                    b"scatterStyle" => {
                         if let Some(val) = get_attr(e, b"val") {
                             style = match val.as_slice() {
                                 b"line" => ScatterStyle::Line,
@@ -486,10 +529,10 @@ fn parse_scatter_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Scat
                             };
                         }
                     },
-                    b"c:varyColors" => {
+                    b"varyColors" => {
                         common.vary_colors = parse_bool_attr(e).unwrap_or(false);
                     },
-                    b"c:ser" => {
+                    b"ser" => {
                         if let Some(series) = parse_series(reader)? {
                             common.series.push(series);
                         }
@@ -497,7 +540,7 @@ fn parse_scatter_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Scat
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:scatterChart" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"scatterChart" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -513,34 +556,65 @@ fn parse_scatter_chart<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Scat
 fn parse_series<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Series>> {
     let mut series = Series::new(0);
     let mut buf = Vec::new();
+    let mut skip_buf = Vec::new();
 
     loop {
         match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+            Ok(Event::Start(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:idx" => {
+                    b"tx" => {
+                        series.title = parse_data_source(reader, b"tx")?.into_title();
+                    },
+                    b"cat" => {
+                        series.categories = parse_data_source(reader, b"cat")?.into_string_data();
+                    },
+                    b"val" => {
+                        series.values = parse_data_source(reader, b"val")?.into_numeric_data();
+                    },
+                    b"xVal" => {
+                        series.x_values = parse_data_source(reader, b"xVal")?.into_numeric_data();
+                    },
+                    b"yVal" => {
+                        series.y_values = parse_data_source(reader, b"yVal")?.into_numeric_data();
+                    },
+                    b"bubbleSize" => {
+                        series.bubble_sizes =
+                            parse_data_source(reader, b"bubbleSize")?.into_numeric_data();
+                    },
+                    // These subtrees carry their own `tx`/`val` elements that must not
+                    // be mistaken for the series data.
+                    b"spPr" | b"dPt" | b"dLbls" | b"trendline" | b"errBars" | b"extLst" => {
+                        reader
+                            .read_to_end_into(e.name(), &mut skip_buf)
+                            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                        skip_buf.clear();
+                    },
+                    _ => {},
+                }
+            },
+            Ok(Event::Empty(ref e)) => {
+                let tag_name = e.local_name();
+                match tag_name.as_ref() {
+                    b"idx" => {
                         series.index = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:order" => {
+                    b"order" => {
                         series.order = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:cat" => {
-                        series.categories = parse_string_data(reader)?;
-                    },
-                    b"c:val" => {
-                        series.values = parse_numeric_data(reader)?;
+                    b"smooth" => {
+                        series.smooth = parse_bool_attr(e)?;
                     },
-                    b"c:xVal" => {
-                        series.x_values = parse_numeric_data(reader)?;
+                    b"invertIfNegative" => {
+                        series.invert_if_negative = parse_bool_attr(e)?;
                     },
-                    b"c:yVal" => {
-                        series.y_values = parse_numeric_data(reader)?;
+                    b"explosion" => {
+                        series.explosion = parse_u32_attr(e, b"val");
                     },
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:ser" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"ser" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -551,70 +625,127 @@ fn parse_series<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Series>> {
     Ok(Some(series))
 }
 
-fn parse_string_data<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<StringData>> {
-    let mut data = StringData::from_values(Vec::new());
-    let mut buf = Vec::new();
+/// A series data source (`c:tx`, `c:cat`, `c:val`, ...) as stored in the XML.
+///
+/// Covers both references (`c:strRef`/`c:numRef`, with their optional caches)
+/// and literals (`c:strLit`/`c:numLit`, or a bare `c:v` for series names).
+#[derive(Debug, Default)]
+struct RawDataSource {
+    formula: Option<String>,
+    format_code: Option<String>,
+    point_count: Option<usize>,
+    points: Vec<(usize, String)>,
+}
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"c:pt" => {
-                if let Some(text) = parse_point_text(reader)? {
-                    data.values.push(text);
-                }
-            },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:cat" => break,
-            Ok(Event::Eof) => break,
-            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
-            _ => {},
+impl RawDataSource {
+    /// Cached point values laid out by index, with gaps left empty.
+    fn into_values(self) -> (Option<String>, Option<String>, Vec<String>) {
+        let len = self
+            .points
+            .iter()
+            .map(|(idx, _)| idx + 1)
+            .max()
+            .unwrap_or(0)
+            .max(self.point_count.unwrap_or(0));
+        let mut values: Vec<Option<String>> = vec![None; len];
+        for (idx, value) in self.points {
+            // Multi-level categories repeat indices per level; the first level
+            // holds the labels closest to the axis.
+            if values[idx].is_none() {
+                values[idx] = Some(value);
+            }
         }
-        buf.clear();
+        let values = values.into_iter().map(Option::unwrap_or_default).collect();
+        (self.formula, self.format_code, values)
     }
 
-    Ok(Some(data))
-}
-
-fn parse_numeric_data<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<NumericData>> {
-    let mut data = NumericData::from_values(Vec::new());
-    let mut buf = Vec::new();
+    fn into_string_data(self) -> Option<StringData> {
+        let (formula, _, values) = self.into_values();
+        if formula.is_none() && values.is_empty() {
+            return None;
+        }
+        Some(StringData {
+            source_ref: formula.map(DataSourceRef::new),
+            values,
+        })
+    }
 
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"c:pt" => {
-                if let Some(val) = parse_point_value(reader)? {
-                    data.values.push(val);
-                }
-            },
-            Ok(Event::End(ref e))
-                if matches!(e.local_name().as_ref(), b"c:val" | b"c:xVal" | b"c:yVal") =>
-            {
-                break;
-            },
-            Ok(Event::Eof) => break,
-            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
-            _ => {},
+    /// Points that are missing or not numeric become `NaN` so indices stay aligned.
+    fn into_numeric_data(self) -> Option<NumericData> {
+        let (formula, format_code, values) = self.into_values();
+        if formula.is_none() && values.is_empty() {
+            return None;
         }
-        buf.clear();
+        Some(NumericData {
+            source_ref: formula.map(DataSourceRef::new),
+            values: values
+                .iter()
+                .map(|v| v.trim().parse::<f64>().unwrap_or(f64::NAN))
+                .collect(),
+            format_code,
+        })
     }
 
-    Ok(Some(data))
+    /// A cached name wins over the reference so callers get displayable text.
+    fn into_title(self) -> Option<TitleText> {
+        let (formula, _, values) = self.into_values();
+        match values.into_iter().next() {
+            Some(text) => Some(TitleText::Literal(RichText::new(text))),
+            None => formula.map(TitleText::from_ref),
+        }
+    }
 }
 
-fn parse_point_text<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<String>> {
-    let mut text = String::new();
+fn parse_data_source<R: BufRead>(reader: &mut Reader<R>, end_tag: &[u8]) -> Result<RawDataSource> {
+    let mut source = RawDataSource::default();
     let mut buf = Vec::new();
-    let mut in_v = false;
+    let mut text = String::new();
+    let mut in_text = false;
+    let mut current_idx: Option<usize> = None;
 
     loop {
         match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) if e.local_name().as_ref() == b"c:v" => {
-                in_v = true;
+            Ok(Event::Start(ref e)) => match e.local_name().as_ref() {
+                b"f" | b"formatCode" | b"v" => {
+                    in_text = true;
+                    text.clear();
+                },
+                b"pt" => {
+                    current_idx = parse_u32_attr(e, b"idx").map(|idx| idx as usize);
+                },
+                _ => {},
+            },
+            Ok(Event::Empty(ref e)) if e.local_name().as_ref() == b"ptCount" => {
+                source.point_count = parse_u32_attr(e, b"val").map(|n| n as usize);
             },
-            Ok(Event::Text(e)) if in_v => {
-                text = std::str::from_utf8(e.as_ref())
-                    .map_err(|e| OoxmlError::Xml(e.to_string()))?
-                    .to_string();
+            Ok(Event::Text(ref e)) if in_text => {
+                text.push_str(&e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?);
+            },
+            Ok(Event::GeneralRef(ref e)) if in_text => {
+                push_general_ref(&mut text, e)?;
+            },
+            Ok(Event::End(ref e)) => {
+                let tag_name = e.local_name();
+                match tag_name.as_ref() {
+                    b"f" => {
+                        in_text = false;
+                        source.formula = Some(std::mem::take(&mut text));
+                    },
+                    b"formatCode" => {
+                        in_text = false;
+                        source.format_code = Some(std::mem::take(&mut text));
+                    },
+                    b"v" => {
+                        in_text = false;
+                        // A bare `c:v` (series name literal) has no enclosing point.
+                        let idx = current_idx.unwrap_or(source.points.len());
+                        source.points.push((idx, std::mem::take(&mut text)));
+                    },
+                    b"pt" => current_idx = None,
+                    name if name == end_tag => break,
+                    _ => {},
+                }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:pt" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -622,15 +753,23 @@ fn parse_point_text<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<String>
         buf.clear();
     }
 
-    Ok(Some(text))
+    Ok(source)
 }
 
-fn parse_point_value<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<f64>> {
-    if let Some(text) = parse_point_text(reader)? {
-        Ok(text.parse::<f64>().ok())
+#[inline]
+fn push_general_ref(target: &mut String, e: &BytesRef) -> Result<()> {
+    if let Some(ch) = e
+        .resolve_char_ref()
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?
+    {
+        target.push(ch);
     } else {
-        Ok(None)
+        let name = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        if let Some(resolved) = quick_xml::escape::resolve_predefined_entity(&name) {
+            target.push_str(resolved);
+        }
     }
+    Ok(())
 }
 
 fn parse_category_axis<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<CategoryAxis>> {
@@ -644,19 +783,19 @@ fn parse_category_axis<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Cate
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:axId" => {
+                    b"axId" => {
                         axis_id = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:crossAx" => {
+                    b"crossAx" => {
                         cross_axis_id = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:axPos" => {
+                    b"axPos" => {
                         position = parse_axis_position(e);
                     },
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:catAx" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"catAx" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -678,19 +817,19 @@ fn parse_value_axis<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<ValueAx
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:axId" => {
+                    b"axId" => {
                         axis_id = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:crossAx" => {
+                    b"crossAx" => {
                         cross_axis_id = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:axPos" => {
+                    b"axPos" => {
                         position = parse_axis_position(e);
                     },
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:valAx" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"valAx" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -712,19 +851,19 @@ fn parse_date_axis<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<DateAxis
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:axId" => {
+                    b"axId" => {
                         axis_id = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:crossAx" => {
+                    b"crossAx" => {
                         cross_axis_id = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:axPos" => {
+                    b"axPos" => {
                         position = parse_axis_position(e);
                     },
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:dateAx" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"dateAx" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -746,19 +885,19 @@ fn parse_series_axis<R: BufRead>(reader: &mut Reader<R>) -> Result<Option<Series
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:axId" => {
+                    b"axId" => {
                         axis_id = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:crossAx" => {
+                    b"crossAx" => {
                         cross_axis_id = parse_u32_attr(e, b"val").unwrap_or(0);
                     },
-                    b"c:axPos" => {
+                    b"axPos" => {
                         position = parse_axis_position(e);
                     },
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:serAx" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"serAx" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -779,7 +918,7 @@ fn parse_legend<R: BufRead>(reader: &mut Reader<R>) -> Result<Legend> {
             Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
                 let tag_name = e.local_name();
                 match tag_name.as_ref() {
-                    b"c:legendPos" => {
+                    b"legendPos" => {
                         if let Some(val) = get_attr(e, b"val") {
                             position = match val.as_slice() {
                                 b"b" => LegendPosition::Bottom,
@@ -791,13 +930,13 @@ fn parse_legend<R: BufRead>(reader: &mut Reader<R>) -> Result<Legend> {
                             };
                         }
                     },
-                    b"c:overlay" => {
+                    b"overlay" => {
                         overlay = parse_bool_attr(e).unwrap_or(false);
                     },
                     _ => {},
                 }
             },
-            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"c:legend" => break,
+            Ok(Event::End(ref e)) if e.local_name().as_ref() == b"legend" => break,
             Ok(Event::Eof) => break,
             Err(e) => return Err(OoxmlError::Xml(e.to_string())),
             _ => {},
@@ -872,3 +1011,88 @@ fn get_attr(e: &BytesStart, name: &[u8]) -> Option<Vec<u8>> {
         .find(|a| a.key.as_ref() == name)
         .map(|a| a.value.to_vec())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BAR_CHART: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+  <c:chart>
+    <c:title><c:tx><c:rich><a:bodyPr/><a:p><a:r><a:t>Sales &amp; Costs</a:t></a:r></a:p></c:rich></c:tx><c:overlay val="0"/></c:title>
+    <c:autoTitleDeleted val="0"/>
+    <c:plotArea>
+      <c:barChart>
+        <c:barDir val="bar"/>
+        <c:grouping val="clustered"/>
+        <c:ser>
+          <c:idx val="0"/><c:order val="0"/>
+          <c:tx><c:strRef><c:f>Sheet1!$B$1</c:f><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>Revenue</c:v></c:pt></c:strCache></c:strRef></c:tx>
+          <c:dLbls><c:tx><c:rich><a:p><a:r><a:t>label</a:t></a:r></a:p></c:rich></c:tx></c:dLbls>
+          <c:cat><c:strRef><c:f>Sheet1!$A$2:$A$4</c:f><c:strCache><c:ptCount val="3"/><c:pt idx="0"><c:v>North</c:v></c:pt><c:pt idx="2"><c:v>R&amp;D</c:v></c:pt></c:strCache></c:strRef></c:cat>
+          <c:val><c:numRef><c:f>Sheet1!$B$2:$B$4</c:f><c:numCache><c:formatCode>0.0</c:formatCode><c:ptCount val="3"/><c:pt idx="0"><c:v>10</c:v></c:pt><c:pt idx="1"><c:v>20.5</c:v></c:pt><c:pt idx="2"><c:v>30</c:v></c:pt></c:numCache></c:numRef></c:val>
+        </c:ser>
+        <c:ser>
+          <c:idx val="1"/><c:order val="1"/>
+          <c:tx><c:v>Literal</c:v></c:tx>
+          <c:val><c:numRef><c:f>Sheet1!$C$2:$C$4</c:f></c:numRef></c:val>
+        </c:ser>
+        <c:axId val="1"/><c:axId val="2"/>
+      </c:barChart>
+      <c:catAx><c:axId val="1"/><c:axPos val="l"/><c:crossAx val="2"/></c:catAx>
+      <c:valAx><c:axId val="2"/><c:axPos val="b"/><c:crossAx val="1"/></c:valAx>
+    </c:plotArea>
+    <c:legend><c:legendPos val="b"/></c:legend>
+  </c:chart>
+</c:chartSpace>"#;
+
+    #[test]
+    fn parse_bar_chart_with_caches() {
+        let chart = parse_chart(BAR_CHART.as_bytes()).unwrap();
+
+        match &chart.title {
+            Some(TitleText::Literal(text)) => assert_eq!(text.text, "Sales & Costs"),
+            other => panic!("unexpected title: {:?}", other),
+        }
+        assert!(!chart.auto_title_deleted);
+        assert_eq!(chart.plot_area.type_groups.len(), 1);
+        assert_eq!(chart.plot_area.axes.len(), 2);
+        assert!(chart.legend.is_some());
+
+        let TypeGroup::Bar(group) = &chart.plot_area.type_groups[0] else {
+            panic!("expected a bar chart");
+        };
+        assert_eq!(group.direction, BarDirection::Bar);
+
+        let series: Vec<_> = chart.plot_area.series().collect();
+        assert_eq!(series.len(), 2);
+
+        let first = series[0];
+        match &first.title {
+            Some(TitleText::Literal(text)) => assert_eq!(text.text, "Revenue"),
+            other => panic!("unexpected series title: {:?}", other),
+        }
+        let categories = first.categories.as_ref().unwrap();
+        assert_eq!(
+            categories.source_ref.as_ref().unwrap().formula,
+            "Sheet1!$A$2:$A$4"
+        );
+        assert_eq!(categories.values, vec!["North", "", "R&D"]);
+        let values = first.values.as_ref().unwrap();
+        assert_eq!(values.values, vec![10.0, 20.5, 30.0]);
+        assert_eq!(values.format_code.as_deref(), Some("0.0"));
+
+        let second = series[1];
+        assert_eq!(second.index, 1);
+        match &second.title {
+            Some(TitleText::Literal(text)) => assert_eq!(text.text, "Literal"),
+            other => panic!("unexpected series title: {:?}", other),
+        }
+        let values = second.values.as_ref().unwrap();
+        assert_eq!(
+            values.source_ref.as_ref().unwrap().formula,
+            "Sheet1!$C$2:$C$4"
+        );
+        assert!(values.values.is_empty());
+    }
+}
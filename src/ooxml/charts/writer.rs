@@ -773,6 +773,16 @@ fn write_string_data_ref<W: Write>(
     Ok(())
 }
 
+/// Write numeric data points, leaving out blank (non-finite) points.
+fn write_numeric_points<W: Write>(writer: &mut W, values: &[f64]) -> std::io::Result<()> {
+    for (i, val) in values.iter().enumerate() {
+        if val.is_finite() {
+            write!(writer, r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#, i, val)?;
+        }
+    }
+    Ok(())
+}
+
 fn write_numeric_data_ref<W: Write>(
     writer: &mut W,
     tag: &str,
@@ -792,9 +802,7 @@ fn write_numeric_data_ref<W: Write>(
                 escape_xml(data.format_code.as_deref().unwrap_or("General"))
             )?;
             write!(writer, r#"<c:ptCount val="{}"/>"#, data.values.len())?;
-            for (i, val) in data.values.iter().enumerate() {
-                write!(writer, r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#, i, val)?;
-            }
+            write_numeric_points(writer, &data.values)?;
             write!(writer, "</c:numCache>")?;
         }
 
@@ -807,9 +815,7 @@ fn write_numeric_data_ref<W: Write>(
             escape_xml(data.format_code.as_deref().unwrap_or("General"))
         )?;
         write!(writer, r#"<c:ptCount val="{}"/>"#, data.values.len())?;
-        for (i, val) in data.values.iter().enumerate() {
-            write!(writer, r#"<c:pt idx="{}"><c:v>{}</c:v></c:pt>"#, i, val)?;
-        }
+        write_numeric_points(writer, &data.values)?;
         write!(writer, "</c:numLit>")?;
     }
 
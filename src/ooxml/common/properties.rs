@@ -3,12 +3,24 @@
 //! This module provides document metadata properties that are shared across
 //! DOCX, XLSX, and PPTX formats.
 
+use crate::common::Metadata;
 use crate::common::xml::escape_xml;
+use crate::ooxml::error::{OoxmlError, Result};
+use crate::ooxml::opc::part::BlobPart;
+use crate::ooxml::opc::{OpcPackage, PackURI};
 use chrono::{DateTime, Utc};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::collections::HashMap;
+
+/// Name written to `<Application>` in app.xml when none is set.
+const DEFAULT_APPLICATION: &str = "The Litchi Rust Library";
 
 /// Document core properties (metadata).
 ///
 /// These properties are stored in the `docProps/core.xml` file in the OPC package.
+/// The application, company, manager and template fields are extended
+/// properties and go to `docProps/app.xml` instead.
 #[derive(Debug, Clone, Default)]
 pub struct DocumentProperties {
     /// Document title
@@ -33,6 +45,36 @@ pub struct DocumentProperties {
     pub created: Option<DateTime<Utc>>,
     /// Last modification date
     pub modified: Option<DateTime<Utc>>,
+    /// Name of the application that produced the document (app.xml)
+    pub application: Option<String>,
+    /// Company name (app.xml)
+    pub company: Option<String>,
+    /// Manager name (app.xml)
+    pub manager: Option<String>,
+    /// Template the document is based on (app.xml)
+    pub template: Option<String>,
+}
+
+impl From<&Metadata> for DocumentProperties {
+    fn from(metadata: &Metadata) -> Self {
+        Self {
+            title: metadata.title.clone(),
+            subject: metadata.subject.clone(),
+            creator: metadata.author.clone(),
+            keywords: metadata.keywords.clone(),
+            description: metadata.description.clone(),
+            last_modified_by: metadata.last_modified_by.clone(),
+            category: metadata.category.clone(),
+            content_status: metadata.content_status.clone(),
+            language: metadata.language.clone(),
+            created: metadata.created,
+            modified: metadata.modified,
+            application: metadata.application.clone(),
+            company: metadata.company.clone(),
+            manager: metadata.manager.clone(),
+            template: metadata.template.clone(),
+        }
+    }
 }
 
 impl DocumentProperties {
@@ -41,6 +83,19 @@ impl DocumentProperties {
         Self::default()
     }
 
+    /// Load the core and extended properties of an existing package.
+    ///
+    /// Missing or malformed property parts yield empty properties. The
+    /// modification date is left unset so that saving stamps the current time.
+    pub fn from_package(package: &OpcPackage) -> Self {
+        crate::ooxml::metadata::extract_metadata(package)
+            .map(|metadata| Self {
+                modified: None,
+                ..Self::from(&metadata)
+            })
+            .unwrap_or_default()
+    }
+
     /// Set the document title.
     pub fn title(mut self, title: &str) -> Self {
         self.title = Some(title.to_string());
@@ -95,6 +150,99 @@ impl DocumentProperties {
         self
     }
 
+    /// Set the creation date.
+    pub fn created(mut self, created: DateTime<Utc>) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    /// Set the last modification date.
+    pub fn modified(mut self, modified: DateTime<Utc>) -> Self {
+        self.modified = Some(modified);
+        self
+    }
+
+    /// Set the company name.
+    pub fn company(mut self, company: &str) -> Self {
+        self.company = Some(company.to_string());
+        self
+    }
+
+    /// Set the manager name.
+    pub fn manager(mut self, manager: &str) -> Self {
+        self.manager = Some(manager.to_string());
+        self
+    }
+
+    /// Fill in missing creation and modification dates with the current time.
+    ///
+    /// Writers call this right before serializing core.xml so saved files
+    /// always carry both timestamps.
+    pub fn stamp(&mut self) {
+        let now = Utc::now();
+        self.created.get_or_insert(now);
+        self.modified.get_or_insert(now);
+    }
+
+    /// Merge the extended properties into an existing app.xml.
+    ///
+    /// Set fields replace the matching element (or are appended when the
+    /// element is missing); everything else in `xml` is kept as-is. A missing
+    /// `<Application>` is filled with the library name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `xml` is malformed or has no root element.
+    pub fn merge_app_xml(&self, xml: &str) -> Result<String> {
+        let app = AppXml::scan(xml)?;
+        let application = self
+            .application
+            .as_deref()
+            .or_else(|| (!app.children.contains_key("Application")).then_some(DEFAULT_APPLICATION));
+        let fields = [
+            ("Application", application),
+            ("Company", self.company.as_deref()),
+            ("Manager", self.manager.as_deref()),
+            ("Template", self.template.as_deref()),
+        ];
+
+        let prefix = app.prefix.as_str();
+        let mut splices = Vec::new();
+        let mut appended = String::new();
+        for (name, value) in fields {
+            let Some(value) = value else {
+                continue;
+            };
+            let element = format!("<{prefix}{name}>{}</{prefix}{name}>", escape_xml(value));
+            match app.children.get(name) {
+                Some(&(start, end)) => splices.push((start, end, element)),
+                None => appended.push_str(&element),
+            }
+        }
+        if !appended.is_empty() {
+            match app.close {
+                Some(close) => splices.push((close, close, appended)),
+                None => {
+                    // Expand a self-closing root to hold the new elements
+                    let (start, end) = app.root;
+                    let tag = xml[start..end].trim_end_matches("/>").trim_end();
+                    splices.push((start, end, format!("{tag}>{appended}</{}>", app.root_name)));
+                },
+            }
+        }
+
+        splices.sort_by_key(|&(start, _, _)| start);
+        let mut merged = String::with_capacity(xml.len() + 256);
+        let mut cursor = 0;
+        for (start, end, text) in splices {
+            merged.push_str(&xml[cursor..start]);
+            merged.push_str(&text);
+            cursor = end;
+        }
+        merged.push_str(&xml[cursor..]);
+        Ok(merged)
+    }
+
     /// Generate core.xml content for this properties set.
     pub fn to_xml(&self) -> String {
         let mut xml = String::with_capacity(1024);
@@ -167,20 +315,165 @@ impl DocumentProperties {
         // Created date
         if let Some(ref created) = self.created {
             xml.push_str("<dcterms:created xsi:type=\"dcterms:W3CDTF\">");
-            xml.push_str(&created.to_rfc3339());
+            xml.push_str(&format_w3cdtf(created));
             xml.push_str("</dcterms:created>");
         }
 
         // Modified date
         if let Some(ref modified) = self.modified {
             xml.push_str("<dcterms:modified xsi:type=\"dcterms:W3CDTF\">");
-            xml.push_str(&modified.to_rfc3339());
+            xml.push_str(&format_w3cdtf(modified));
             xml.push_str("</dcterms:modified>");
         }
 
         xml.push_str("</cp:coreProperties>");
         xml
     }
+
+    /// Generate a minimal app.xml holding only the extended properties.
+    pub fn to_app_xml(&self) -> Result<String> {
+        self.merge_app_xml(concat!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+            r#"<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties" "#,
+            r#"xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">"#,
+            "</Properties>"
+        ))
+    }
+}
+
+/// Write a document property part (core.xml, app.xml or custom.xml).
+///
+/// The part the package already relates with `reltype` is replaced in place;
+/// otherwise the part is created at `default_partname` and related from the
+/// package. `build` receives the current part content, if any.
+pub(crate) fn write_property_part(
+    package: &mut OpcPackage,
+    reltype: &str,
+    default_partname: &str,
+    content_type: &str,
+    build: impl FnOnce(Option<&[u8]>) -> Result<String>,
+) -> Result<()> {
+    let existing = package
+        .rels()
+        .part_with_reltype(reltype)
+        .ok()
+        .and_then(|rel| rel.target_partname().ok());
+
+    let partname = match existing {
+        Some(partname) => partname,
+        None => {
+            package.relate_to(default_partname.trim_start_matches('/'), reltype);
+            PackURI::new(default_partname).map_err(OoxmlError::InvalidUri)?
+        },
+    };
+
+    let current = package
+        .get_part(&partname)
+        .ok()
+        .map(|part| part.try_blob())
+        .transpose()?;
+    let xml = build(current)?;
+    package.add_part(Box::new(BlobPart::new(
+        partname,
+        content_type.to_string(),
+        xml.into_bytes(),
+    )));
+    Ok(())
+}
+
+/// Format a timestamp as W3CDTF, the UTC form Office writes in core.xml.
+fn format_w3cdtf(date: &DateTime<Utc>) -> String {
+    date.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+/// Byte positions of the elements in an app.xml document.
+struct AppXml {
+    /// Qualified name of the root element
+    root_name: String,
+    /// Namespace prefix of the root element, including the colon
+    prefix: String,
+    /// Start tag of the root element
+    root: (usize, usize),
+    /// Start of the root end tag, `None` for a self-closing root
+    close: Option<usize>,
+    /// First child element of the root with each local name
+    children: HashMap<String, (usize, usize)>,
+}
+
+impl AppXml {
+    fn scan(xml: &str) -> Result<Self> {
+        let mut reader = Reader::from_str(xml);
+        let mut depth = 0usize;
+        let mut app: Option<AppXml> = None;
+        let mut child: Option<(String, usize)> = None;
+
+        loop {
+            let start = reader.buffer_position() as usize;
+            let event = reader.read_event()?;
+            let end = reader.buffer_position() as usize;
+            let empty = matches!(event, Event::Empty(_));
+            match event {
+                Event::Start(e) | Event::Empty(e) if depth == 0 => {
+                    let name = e.name();
+                    app = Some(AppXml {
+                        root_name: String::from_utf8_lossy(name.as_ref()).into_owned(),
+                        prefix: name
+                            .prefix()
+                            .map(|p| format!("{}:", String::from_utf8_lossy(p.as_ref())))
+                            .unwrap_or_default(),
+                        root: (start, end),
+                        close: None,
+                        children: HashMap::new(),
+                    });
+                    if empty {
+                        break;
+                    }
+                    depth += 1;
+                },
+                Event::Start(e) => {
+                    if depth == 1 {
+                        let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                        child = Some((name, start));
+                    }
+                    depth += 1;
+                },
+                Event::Empty(e) if depth == 1 => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                    if let Some(app) = app.as_mut() {
+                        app.children.entry(name).or_insert((start, end));
+                    }
+                },
+                Event::End(_) => {
+                    depth = depth.saturating_sub(1);
+                    match depth {
+                        0 => {
+                            if let Some(app) = app.as_mut() {
+                                app.close = Some(start);
+                            }
+                            break;
+                        },
+                        1 => {
+                            if let (Some(app), Some((name, child_start))) =
+                                (app.as_mut(), child.take())
+                            {
+                                app.children.entry(name).or_insert((child_start, end));
+                            }
+                        },
+                        _ => {},
+                    }
+                },
+                Event::Eof if depth > 0 => {
+                    return Err(OoxmlError::Xml(
+                        "app.xml ends inside an element".to_string(),
+                    ));
+                },
+                Event::Eof => break,
+                _ => {},
+            }
+        }
+
+        app.ok_or_else(|| OoxmlError::Xml("app.xml has no root element".to_string()))
+    }
 }
 
 #[cfg(test)]
@@ -222,4 +515,65 @@ mod tests {
         assert!(xml.contains("&gt;"));
         assert!(xml.contains("&quot;"));
     }
+
+    #[test]
+    fn test_dates_use_w3cdtf() {
+        use chrono::TimeZone;
+
+        let created = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let mut props = DocumentProperties::new().created(created);
+        props.stamp();
+
+        let xml = props.to_xml();
+        assert!(xml.contains(
+            r#"<dcterms:created xsi:type="dcterms:W3CDTF">2024-01-02T03:04:05Z</dcterms:created>"#
+        ));
+        assert!(xml.contains("<dcterms:modified"));
+        assert_eq!(props.created, Some(created));
+    }
+
+    #[test]
+    fn test_merge_app_xml() {
+        let props = DocumentProperties::new()
+            .company("Acme & Co")
+            .manager("Jane");
+        let xml = props
+            .merge_app_xml(
+                "<Properties><Application>Word</Application><Company/><Pages>3</Pages></Properties>",
+            )
+            .unwrap();
+
+        assert_eq!(
+            xml,
+            "<Properties><Application>Word</Application><Company>Acme &amp; Co</Company>\
+             <Pages>3</Pages><Manager>Jane</Manager></Properties>"
+        );
+        assert!(
+            DocumentProperties::new()
+                .to_app_xml()
+                .unwrap()
+                .contains("<Application>The Litchi Rust Library</Application>")
+        );
+
+        // Only children of the root are replaced, keeping its prefix
+        let xml = props
+            .merge_app_xml(
+                "<ep:Properties xmlns:ep=\"x\"><ep:HeadingPairs><ep:Company>Old</ep:Company>\
+                 </ep:HeadingPairs><ep:Company>Old</ep:Company></ep:Properties>",
+            )
+            .unwrap();
+        assert_eq!(
+            xml,
+            "<ep:Properties xmlns:ep=\"x\"><ep:HeadingPairs><ep:Company>Old</ep:Company>\
+             </ep:HeadingPairs><ep:Company>Acme &amp; Co</ep:Company>\
+             <ep:Application>The Litchi Rust Library</ep:Application><ep:Manager>Jane</ep:Manager>\
+             </ep:Properties>"
+        );
+        assert_eq!(
+            props.merge_app_xml("<Properties />").unwrap(),
+            "<Properties><Application>The Litchi Rust Library</Application>\
+             <Company>Acme &amp; Co</Company><Manager>Jane</Manager></Properties>"
+        );
+        assert!(props.merge_app_xml("<Properties><Company>").is_err());
+    }
 }
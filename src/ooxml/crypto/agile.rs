@@ -370,7 +370,7 @@ pub fn decrypt_ooxml_package_agile(
     let major = u16::from_le_bytes([encryption_info[0], encryption_info[1]]);
     let minor = u16::from_le_bytes([encryption_info[2], encryption_info[3]]);
     if major != AGILE_ENCRYPTION_VERSION_MAJOR || minor != AGILE_ENCRYPTION_VERSION_MINOR {
-        return Err(OoxmlError::InvalidFormat(format!(
+        return Err(OoxmlError::UnsupportedEncryption(format!(
             "unsupported Agile EncryptionInfo version: {}.{}",
             major, minor
         )));
@@ -436,7 +436,7 @@ fn parse_agile_encryption_info(xml_bytes: &[u8]) -> Result<AgileEncryptionInfo>
                         )
                     })?;
                     if block_size != AGILE_BLOCK_SIZE {
-                        return Err(OoxmlError::InvalidFormat(format!(
+                        return Err(OoxmlError::UnsupportedEncryption(format!(
                             "unsupported Agile keyData blockSize: {}",
                             block_size
                         )));
@@ -544,7 +544,7 @@ fn parse_agile_encryption_info(xml_bytes: &[u8]) -> Result<AgileEncryptionInfo>
                         )
                     })?;
                     if block_size != AGILE_BLOCK_SIZE {
-                        return Err(OoxmlError::InvalidFormat(format!(
+                        return Err(OoxmlError::UnsupportedEncryption(format!(
                             "unsupported Agile encryptedKey blockSize: {}",
                             block_size
                         )));
@@ -660,7 +660,7 @@ fn derive_agile_content_key_and_verify(
     sha.update(&verifier);
     let expected = sha.finalize().to_vec();
     if verifier_hash.len() < expected.len() || verifier_hash[..expected.len()] != expected[..] {
-        return Err(OoxmlError::InvalidFormat(
+        return Err(OoxmlError::IncorrectPassword(
             "incorrect password for Agile encrypted OOXML package".to_string(),
         ));
     }
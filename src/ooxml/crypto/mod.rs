@@ -5,8 +5,9 @@ pub mod standard2007;
 pub use agile::{decrypt_ooxml_package_agile, encrypt_ooxml_package_agile};
 pub use standard2007::{decrypt_ooxml_package_standard_2007, encrypt_ooxml_package_standard_2007};
 
-use crate::ole::is_ole_file;
+use crate::ole::{OleFile, is_ole_file};
 use crate::ooxml::error::{OoxmlError, Result};
+use std::io::{Read, Seek};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum EncryptionMode {
@@ -24,16 +25,35 @@ pub fn decrypt_ooxml_ole_encrypted(ole_bytes: &[u8], password: &str) -> Result<D
     let (encryption_info, encrypted_package) =
         ole_encrypted_package::parse_ole_encrypted_package(ole_bytes)?;
 
-    let mode = detect_encryption_mode(&encryption_info)?;
+    decrypt_streams(&encryption_info, &encrypted_package, password)
+}
+
+/// Decrypt the OOXML package held by an already opened OLE container.
+pub fn decrypt_ooxml_ole_file<R: Read + Seek>(
+    ole: &mut OleFile<R>,
+    password: &str,
+) -> Result<DecryptedPackage> {
+    let (encryption_info, encrypted_package) =
+        ole_encrypted_package::read_encrypted_package_streams(ole)?;
+
+    decrypt_streams(&encryption_info, &encrypted_package, password)
+}
+
+fn decrypt_streams(
+    encryption_info: &[u8],
+    encrypted_package: &[u8],
+    password: &str,
+) -> Result<DecryptedPackage> {
+    let mode = detect_encryption_mode(encryption_info)?;
 
     let package_bytes = match mode {
         EncryptionMode::Standard2007 => standard2007::decrypt_ooxml_package_standard_2007(
-            &encryption_info,
-            &encrypted_package,
+            encryption_info,
+            encrypted_package,
             password,
         )?,
         EncryptionMode::Agile => {
-            agile::decrypt_ooxml_package_agile(&encryption_info, &encrypted_package, password)?
+            agile::decrypt_ooxml_package_agile(encryption_info, encrypted_package, password)?
         },
     };
 
@@ -67,7 +87,7 @@ fn detect_encryption_mode(encryption_info: &[u8]) -> Result<EncryptionMode> {
     match (major, minor) {
         (3, 2) => Ok(EncryptionMode::Standard2007),
         (4, 4) => Ok(EncryptionMode::Agile),
-        _ => Err(OoxmlError::InvalidFormat(format!(
+        _ => Err(OoxmlError::UnsupportedEncryption(format!(
             "unsupported OOXML EncryptionInfo version: {}.{}",
             major, minor
         ))),
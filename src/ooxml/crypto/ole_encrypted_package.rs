@@ -1,6 +1,7 @@
 use crate::ole::OleFile;
 use crate::ole::writer::OleWriter;
 use crate::ooxml::error::{OoxmlError, Result};
+use std::io::{Read, Seek};
 
 /// Build an OLE compound file that wraps the given OOXML `EncryptionInfo`
 /// and `EncryptedPackage` streams with the standard StrongEncryptionDataSpace
@@ -93,6 +94,13 @@ pub(crate) fn parse_ole_encrypted_package(bytes: &[u8]) -> Result<(Vec<u8>, Vec<
         OoxmlError::InvalidFormat(format!("invalid OLE container for encrypted OOXML: {}", e))
     })?;
 
+    read_encrypted_package_streams(&mut ole)
+}
+
+/// Read the `EncryptionInfo` and `EncryptedPackage` streams from an open container.
+pub(crate) fn read_encrypted_package_streams<R: Read + Seek>(
+    ole: &mut OleFile<R>,
+) -> Result<(Vec<u8>, Vec<u8>)> {
     let encryption_info = ole.open_stream(&["EncryptionInfo"]).map_err(|e| {
         OoxmlError::InvalidFormat(format!("failed to read EncryptionInfo stream: {}", e))
     })?;
@@ -232,7 +232,7 @@ fn parse_encryption_info_standard2007(info: &[u8]) -> Result<Standard2007Verifie
     let version_major = u16::from_le_bytes([info[0], info[1]]);
     let version_minor = u16::from_le_bytes([info[2], info[3]]);
     if version_major != 3 || version_minor != 2 {
-        return Err(OoxmlError::InvalidFormat(format!(
+        return Err(OoxmlError::UnsupportedEncryption(format!(
             "unsupported Standard 2007 EncryptionInfo version: {}.{}",
             version_major, version_minor
         )));
@@ -321,7 +321,7 @@ fn verify_standard2007_password(key: &[u8], verifier: &Standard2007Verifier) ->
     }
 
     if decrypted_hash[..verifier_hash.len()] != verifier_hash[..] {
-        return Err(OoxmlError::InvalidFormat(
+        return Err(OoxmlError::IncorrectPassword(
             "incorrect password for Standard 2007 encrypted OOXML package".to_string(),
         ));
     }
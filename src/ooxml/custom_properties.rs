@@ -53,7 +53,7 @@ use crate::ooxml::opc::{OpcPackage, PackURI};
 use chrono::{DateTime, Utc};
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use quick_xml::{Reader, Writer};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Cursor;
 
 /// Fixed GUID format ID for custom properties as per OOXML specification.
@@ -112,13 +112,8 @@ impl PropertyValue {
             PropertyValue::Float(f) => f.to_string(),
             PropertyValue::Double(d) => d.to_string(),
             PropertyValue::Boolean(b) => b.to_string(),
-            PropertyValue::DateTime(dt) => {
-                // FILETIME is Windows epoch (100-nanosecond intervals since 1601-01-01)
-                const WINDOWS_EPOCH_OFFSET: i64 = 116_444_736_000_000_000;
-                let unix_nanos = dt.timestamp_nanos_opt().unwrap_or(0);
-                let windows_filetime = (unix_nanos / 100) + WINDOWS_EPOCH_OFFSET;
-                windows_filetime.to_string()
-            },
+            // Office expects `vt:filetime` as a W3CDTF string, not a tick count
+            PropertyValue::DateTime(dt) => dt.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
         }
     }
 
@@ -154,6 +149,11 @@ impl PropertyValue {
                 }
             },
             "filetime" => {
+                // Office writes `vt:filetime` as an xsd:dateTime string
+                if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+                    return Ok(PropertyValue::DateTime(dt.with_timezone(&Utc)));
+                }
+
                 // Parse Windows FILETIME to DateTime
                 const WINDOWS_EPOCH_OFFSET: i64 = 116_444_736_000_000_000;
                 let filetime = text.parse::<i64>().map_err(|e| {
@@ -177,6 +177,47 @@ impl PropertyValue {
     }
 }
 
+impl From<PropertyValue> for crate::common::CustomPropertyValue {
+    fn from(value: PropertyValue) -> Self {
+        match value {
+            PropertyValue::String(s) => Self::String(s),
+            PropertyValue::Integer(i) => Self::Integer(i64::from(i)),
+            PropertyValue::Long(l) => Self::Integer(l),
+            PropertyValue::Float(f) => Self::Float(f64::from(f)),
+            PropertyValue::Double(d) => Self::Float(d),
+            PropertyValue::Boolean(b) => Self::Bool(b),
+            PropertyValue::DateTime(dt) => Self::DateTime(dt),
+        }
+    }
+}
+
+impl From<crate::common::CustomPropertyValue> for PropertyValue {
+    fn from(value: crate::common::CustomPropertyValue) -> Self {
+        use crate::common::CustomPropertyValue;
+
+        match value {
+            CustomPropertyValue::Bool(b) => Self::Boolean(b),
+            CustomPropertyValue::Integer(i) => match i32::try_from(i) {
+                Ok(i) => Self::Integer(i),
+                Err(_) => Self::Long(i),
+            },
+            CustomPropertyValue::Float(f) => Self::Double(f),
+            CustomPropertyValue::DateTime(dt) => Self::DateTime(dt),
+            CustomPropertyValue::String(s) => Self::String(s),
+        }
+    }
+}
+
+impl From<&BTreeMap<String, crate::common::CustomPropertyValue>> for CustomProperties {
+    fn from(values: &BTreeMap<String, crate::common::CustomPropertyValue>) -> Self {
+        let mut props = Self::new();
+        for (name, value) in values {
+            props.add_property(name.clone(), value.clone().into());
+        }
+        props
+    }
+}
+
 /// A single custom property with name, value, and internal ID.
 #[derive(Debug, Clone)]
 struct CustomProperty {
@@ -604,7 +645,7 @@ pub fn extract_custom_properties(package: &OpcPackage) -> Result<CustomPropertie
     // Find the custom properties part
     match find_custom_properties_part(package) {
         Ok(part) => {
-            let xml_content = std::str::from_utf8(part.blob()).map_err(|e| {
+            let xml_content = std::str::from_utf8(part.try_blob()?).map_err(|e| {
                 OoxmlError::Xml(format!("Invalid UTF-8 in custom properties: {}", e))
             })?;
             CustomProperties::from_xml(xml_content)
@@ -154,7 +154,7 @@ impl Comment {
     ///
     /// A vector of comments
     pub(crate) fn extract_from_part(part: &dyn Part) -> Result<Vec<Comment>> {
-        let xml_bytes = part.blob();
+        let xml_bytes = part.try_blob()?;
         let mut reader = Reader::from_reader(xml_bytes);
         reader.config_mut().trim_text(true);
 
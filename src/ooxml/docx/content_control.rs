@@ -2,9 +2,12 @@
 ///
 /// Content controls are structured regions in a document that can contain
 /// specific types of content (text, dates, lists, etc.).
+use crate::common::xml::attr;
 use crate::ooxml::error::{OoxmlError, Result};
+use chrono::NaiveDate;
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesRef, BytesStart, Event};
+use std::ops::Range;
 
 /// A content control in a Word document.
 ///
@@ -21,7 +24,7 @@ use quick_xml::events::Event;
 ///
 /// for control in doc.content_controls()? {
 ///     if let Some(tag) = control.tag() {
-///         println!("Control {}: {}", control.id(), tag);
+///         println!("Control {}: {} = {}", control.id(), tag, control.value());
 ///     }
 /// }
 /// # Ok::<(), Box<dyn std::error::Error>>(())
@@ -40,6 +43,54 @@ pub struct ContentControl {
     lock_delete: bool,
     /// Whether the content can be edited
     lock_content: bool,
+    /// Text currently shown by the control
+    value: String,
+    /// Whether the control shows its placeholder text
+    showing_placeholder: bool,
+    /// Drop-down list or combo box items (display text, value)
+    list_items: Vec<(String, String)>,
+    /// Checkbox state
+    checked: Option<bool>,
+    /// Date picker display format
+    date_format: Option<String>,
+    /// Date picker value
+    date: Option<NaiveDate>,
+    /// Binding to a custom XML part
+    data_binding: Option<DataBinding>,
+}
+
+/// Binding of a content control to a node of a custom XML part.
+///
+/// Word keeps the control and the node in sync: the control shows the node's
+/// text, and editing the control updates the node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataBinding {
+    /// XPath of the bound node
+    pub(crate) xpath: String,
+    /// `ds:itemID` of the custom XML part, when given
+    pub(crate) store_item_id: Option<String>,
+    /// Namespace prefixes used in the XPath (`xmlns:ns0='...'` pairs)
+    pub(crate) prefix_mappings: String,
+}
+
+impl DataBinding {
+    /// Get the XPath of the bound node.
+    #[inline]
+    pub fn xpath(&self) -> &str {
+        &self.xpath
+    }
+
+    /// Get the item ID of the custom XML part holding the node.
+    #[inline]
+    pub fn store_item_id(&self) -> Option<&str> {
+        self.store_item_id.as_deref()
+    }
+
+    /// Get the namespace prefix declarations used by the XPath.
+    #[inline]
+    pub fn prefix_mappings(&self) -> &str {
+        &self.prefix_mappings
+    }
 }
 
 impl ContentControl {
@@ -59,6 +110,13 @@ impl ContentControl {
             control_type,
             lock_delete,
             lock_content,
+            value: String::new(),
+            showing_placeholder: false,
+            list_items: Vec::new(),
+            checked: None,
+            date_format: None,
+            date: None,
+            data_binding: None,
         }
     }
 
@@ -81,6 +139,10 @@ impl ContentControl {
     }
 
     /// Get the control type.
+    ///
+    /// One of `text` (plain text), `richText`, `date`, `comboBox`,
+    /// `dropDownList`, `picture` or `checkbox`. Rich text controls often
+    /// leave their type out, in which case this is `None`.
     #[inline]
     pub fn control_type(&self) -> Option<&str> {
         self.control_type.as_deref()
@@ -98,117 +160,429 @@ impl ContentControl {
         self.lock_content
     }
 
+    /// Get the text the control currently shows.
+    ///
+    /// Paragraphs and line breaks are separated by `\n`. For a checkbox this
+    /// is the check mark glyph; use [`is_checked`](Self::is_checked) instead.
+    #[inline]
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Check if the control shows its placeholder text rather than a value.
+    #[inline]
+    pub fn is_showing_placeholder(&self) -> bool {
+        self.showing_placeholder
+    }
+
+    /// Get the items of a drop-down list or combo box as (display text, value).
+    #[inline]
+    pub fn list_items(&self) -> &[(String, String)] {
+        &self.list_items
+    }
+
+    /// Get the state of a checkbox, or `None` for other controls.
+    #[inline]
+    pub fn is_checked(&self) -> Option<bool> {
+        self.checked
+    }
+
+    /// Get the display format of a date picker (e.g. `M/d/yyyy`).
+    #[inline]
+    pub fn date_format(&self) -> Option<&str> {
+        self.date_format.as_deref()
+    }
+
+    /// Get the date selected in a date picker.
+    #[inline]
+    pub fn date(&self) -> Option<NaiveDate> {
+        self.date
+    }
+
+    /// Get the custom XML binding of the control.
+    #[inline]
+    pub fn data_binding(&self) -> Option<&DataBinding> {
+        self.data_binding.as_ref()
+    }
+
     /// Extract content controls from document XML bytes.
     pub(crate) fn extract_from_document(doc_xml: &[u8]) -> Result<Vec<ContentControl>> {
-        let mut reader = Reader::from_reader(doc_xml);
-        reader.config_mut().trim_text(true);
-
-        let mut controls = Vec::new();
-        let mut in_sdt_pr = false;
-        let mut current_id: Option<u32> = None;
-        let mut current_tag: Option<String> = None;
-        let mut current_title: Option<String> = None;
-        let mut current_type: Option<String> = None;
-        let mut current_lock_delete = false;
-        let mut current_lock_content = false;
-
-        loop {
-            match reader.read_event() {
-                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
-                    match e.local_name().as_ref() {
-                        b"sdtPr" => {
-                            // Content control properties start
-                            in_sdt_pr = true;
-                            current_id = None;
-                            current_tag = None;
-                            current_title = None;
-                            current_type = None;
-                            current_lock_delete = false;
-                            current_lock_content = false;
-                        },
-                        b"id" if in_sdt_pr => {
-                            for attr in e.attributes().flatten() {
-                                if attr.key.local_name().as_ref() == b"val" {
-                                    let id_str = String::from_utf8_lossy(&attr.value);
-                                    current_id =
-                                        atoi_simd::parse::<u32, false, false>(id_str.as_bytes())
-                                            .ok();
-                                }
-                            }
-                        },
-                        b"tag" if in_sdt_pr => {
-                            for attr in e.attributes().flatten() {
-                                if attr.key.local_name().as_ref() == b"val" {
-                                    current_tag =
-                                        Some(String::from_utf8_lossy(&attr.value).into_owned());
-                                }
-                            }
-                        },
-                        b"alias" if in_sdt_pr => {
-                            for attr in e.attributes().flatten() {
-                                if attr.key.local_name().as_ref() == b"val" {
-                                    current_title =
-                                        Some(String::from_utf8_lossy(&attr.value).into_owned());
-                                }
-                            }
-                        },
-                        b"text" if in_sdt_pr => {
-                            current_type = Some("text".to_string());
-                        },
-                        b"date" if in_sdt_pr => {
-                            current_type = Some("date".to_string());
-                        },
-                        b"comboBox" if in_sdt_pr => {
-                            current_type = Some("comboBox".to_string());
-                        },
-                        b"dropDownList" if in_sdt_pr => {
-                            current_type = Some("dropDownList".to_string());
-                        },
-                        b"picture" if in_sdt_pr => {
-                            current_type = Some("picture".to_string());
-                        },
-                        b"lock" if in_sdt_pr => {
-                            for attr in e.attributes().flatten() {
-                                if attr.key.local_name().as_ref() == b"val" {
-                                    let val = String::from_utf8_lossy(&attr.value);
-                                    match val.as_ref() {
-                                        "sdtLocked" => current_lock_delete = true,
-                                        "contentLocked" => current_lock_content = true,
-                                        "sdtContentLocked" => {
-                                            current_lock_delete = true;
-                                            current_lock_content = true;
-                                        },
-                                        _ => {},
-                                    }
-                                }
-                            }
-                        },
-                        _ => {},
-                    }
-                },
-                Ok(Event::End(e)) => {
-                    if e.local_name().as_ref() == b"sdtPr" {
-                        // End of content control properties
-                        if let Some(id) = current_id {
-                            controls.push(ContentControl::new(
-                                id,
-                                current_tag.clone(),
-                                current_title.clone(),
-                                current_type.clone(),
-                                current_lock_delete,
-                                current_lock_content,
-                            ));
-                        }
-                        in_sdt_pr = false;
-                    }
-                },
-                Ok(Event::Eof) => break,
-                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
-                _ => {},
-            }
+        Ok(scan_content_controls(doc_xml)?
+            .into_iter()
+            .map(|(control, _)| control)
+            .collect())
+    }
+}
+
+/// Where the parts of a content control are within the document XML.
+///
+/// Used to rewrite a control's value in place without touching the rest of
+/// the document.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct SdtLayout {
+    /// Namespace prefix of the `sdt` element, with its colon (usually `w:`)
+    pub(crate) prefix: String,
+    /// Whether the control sits among paragraphs rather than inside one
+    pub(crate) block: bool,
+    /// Run properties declared for the control's content
+    pub(crate) run_properties: Option<Range<usize>>,
+    /// The `showingPlcHdr` element
+    pub(crate) placeholder_flag: Option<Range<usize>>,
+    /// The `w14:checked` element
+    pub(crate) checked: Option<Range<usize>>,
+    /// Glyphs of the checked and unchecked states, with their fonts
+    pub(crate) checked_state: Option<(char, Option<String>)>,
+    pub(crate) unchecked_state: Option<(char, Option<String>)>,
+    /// Start tag of the `dropDownList` or `comboBox` element
+    pub(crate) list: Option<Range<usize>>,
+    /// Start tag of the `date` element
+    pub(crate) date: Option<Range<usize>>,
+    /// The whole `sdtContent` element
+    pub(crate) content: Option<Range<usize>>,
+    /// Start tag of the first paragraph inside the content
+    pub(crate) first_paragraph: Option<Range<usize>>,
+    /// Properties of the first paragraph inside the content
+    pub(crate) first_paragraph_properties: Option<Range<usize>>,
+    /// Properties of the first run inside the content
+    pub(crate) first_run_properties: Option<Range<usize>>,
+    /// Whether the content holds table rows or cells
+    pub(crate) has_cells: bool,
+}
+
+/// Element whose byte range is recorded once it is closed.
+#[derive(Debug, Clone, Copy)]
+enum Slot {
+    RunProperties,
+    PlaceholderFlag,
+    Checked,
+    Content,
+    FirstParagraphProperties,
+    FirstRunProperties,
+}
+
+/// A content control being scanned.
+struct Frame {
+    control: ContentControl,
+    layout: SdtLayout,
+    /// Position of the control in the output
+    index: usize,
+    /// Element depth of the `sdt` element
+    depth: usize,
+    in_properties: bool,
+    in_content: bool,
+    /// Element depths of the first run and paragraph while they are open
+    run_depth: Option<usize>,
+    paragraph_depth: Option<usize>,
+    first_run_done: bool,
+    first_paragraph_done: bool,
+}
+
+/// An open element.
+struct Open {
+    local: Vec<u8>,
+    start: usize,
+    slots: Vec<(usize, Slot)>,
+}
+
+/// Find every content control in `xml`, in document order, with its layout.
+pub(crate) fn scan_content_controls(xml: &[u8]) -> Result<Vec<(ContentControl, SdtLayout)>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut stack: Vec<Open> = Vec::new();
+    let mut frames: Vec<Frame> = Vec::new();
+    let mut controls: Vec<Option<(ContentControl, SdtLayout)>> = Vec::new();
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader
+            .read_event()
+            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        let end = reader.buffer_position() as usize;
+
+        match event {
+            Event::Start(ref e) => {
+                let slots = open_element(e, start..end, false, &stack, &mut frames, &mut controls);
+                stack.push(Open {
+                    local: e.local_name().as_ref().to_vec(),
+                    start,
+                    slots,
+                });
+            },
+            Event::Empty(ref e) => {
+                let slots = open_element(e, start..end, true, &stack, &mut frames, &mut controls);
+                let open = Open {
+                    local: e.local_name().as_ref().to_vec(),
+                    start,
+                    slots,
+                };
+                close_element(open, end, &stack, &mut frames, &mut controls);
+            },
+            Event::End(_) => {
+                if let Some(open) = stack.pop() {
+                    close_element(open, end, &stack, &mut frames, &mut controls);
+                }
+            },
+            Event::Text(ref e) if in_text(&stack) => {
+                let text = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                push_content_text(&mut frames, &text);
+            },
+            Event::GeneralRef(ref e) if in_text(&stack) => {
+                let mut text = String::new();
+                push_general_ref(&mut text, e)?;
+                push_content_text(&mut frames, &text);
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok(controls.into_iter().flatten().collect())
+}
+
+/// Whether the innermost open element is a run's text.
+#[inline]
+fn in_text(stack: &[Open]) -> bool {
+    stack.last().is_some_and(|open| open.local == b"t")
+}
+
+fn push_content_text(frames: &mut [Frame], text: &str) {
+    for frame in frames.iter_mut().filter(|frame| frame.in_content) {
+        frame.control.value.push_str(text);
+    }
+}
+
+pub(crate) fn push_general_ref(target: &mut String, e: &BytesRef) -> Result<()> {
+    if let Some(ch) = e
+        .resolve_char_ref()
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?
+    {
+        target.push(ch);
+    } else {
+        let name = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        if let Some(resolved) = quick_xml::escape::resolve_predefined_entity(&name) {
+            target.push_str(resolved);
+        }
+    }
+    Ok(())
+}
+
+/// Whether an on/off property such as `<w:showingPlcHdr/>` is on.
+fn on_off(e: &BytesStart) -> bool {
+    !matches!(attr(e, b"val").as_deref(), Some("0" | "false" | "off"))
+}
+
+/// Parse a `w14:checkedState`/`w14:uncheckedState` element.
+fn check_state(e: &BytesStart) -> Option<(char, Option<String>)> {
+    let code = u32::from_str_radix(&attr(e, b"val")?, 16).ok()?;
+    Some((char::from_u32(code)?, attr(e, b"font")))
+}
+
+/// Handle an element start, returning the slots to fill when it closes.
+fn open_element(
+    e: &BytesStart,
+    tag: Range<usize>,
+    empty: bool,
+    stack: &[Open],
+    frames: &mut Vec<Frame>,
+    controls: &mut Vec<Option<(ContentControl, SdtLayout)>>,
+) -> Vec<(usize, Slot)> {
+    let local = e.local_name();
+    let local = local.as_ref();
+    let depth = stack.len() + 1;
+    let mut slots = Vec::new();
+
+    if local == b"sdt" && !empty {
+        let name = e.name();
+        let prefix = match name.prefix() {
+            Some(prefix) => format!("{}:", String::from_utf8_lossy(prefix.as_ref())),
+            None => String::new(),
+        };
+        frames.push(Frame {
+            control: ContentControl::new(0, None, None, None, false, false),
+            layout: SdtLayout {
+                prefix,
+                block: !stack.iter().any(|open| open.local == b"p"),
+                ..SdtLayout::default()
+            },
+            index: controls.len(),
+            depth,
+            in_properties: false,
+            in_content: false,
+            run_depth: None,
+            paragraph_depth: None,
+            first_run_done: false,
+            first_paragraph_done: false,
+        });
+        controls.push(None);
+        return slots;
+    }
+
+    // Track the content of every control this element is inside of
+    for (index, frame) in frames.iter_mut().enumerate() {
+        if !frame.in_content {
+            continue;
+        }
+        match local {
+            b"r" if !frame.first_run_done && frame.run_depth.is_none() => {
+                frame.run_depth = Some(depth);
+            },
+            b"rPr" if frame.run_depth == Some(depth - 1) => {
+                slots.push((index, Slot::FirstRunProperties));
+            },
+            b"p" if !frame.first_paragraph_done && frame.paragraph_depth.is_none() => {
+                frame.layout.first_paragraph = Some(tag.clone());
+                frame.paragraph_depth = Some(depth);
+            },
+            b"pPr" if frame.paragraph_depth == Some(depth - 1) => {
+                slots.push((index, Slot::FirstParagraphProperties));
+            },
+            b"tc" | b"tr" if depth == frame.depth + 2 => frame.layout.has_cells = true,
+            b"tab" if stack.last().is_some_and(|open| open.local == b"r") => {
+                frame.control.value.push('\t');
+            },
+            b"br" | b"cr" if stack.last().is_some_and(|open| open.local == b"r") => {
+                frame.control.value.push('\n');
+            },
+            _ => {},
         }
+    }
+
+    let Some(index) = frames.len().checked_sub(1) else {
+        return slots;
+    };
+    let frame = &mut frames[index];
+
+    if depth == frame.depth + 1 {
+        match local {
+            b"sdtPr" => frame.in_properties = !empty,
+            b"sdtContent" => {
+                frame.in_content = !empty;
+                slots.push((index, Slot::Content));
+            },
+            _ => {},
+        }
+        return slots;
+    }
+    if !frame.in_properties {
+        return slots;
+    }
+
+    let control = &mut frame.control;
+    let is_property = depth == frame.depth + 2;
+    match local {
+        b"id" if is_property => {
+            // Word writes negative ids as well; keep their bit pattern
+            control.id = attr(e, b"val")
+                .and_then(|id| id.parse::<i64>().ok())
+                .map_or(0, |id| id as u32);
+        },
+        b"tag" if is_property => control.tag = attr(e, b"val"),
+        b"alias" if is_property => control.title = attr(e, b"val"),
+        b"lock" if is_property => match attr(e, b"val").as_deref() {
+            Some("sdtLocked") => control.lock_delete = true,
+            Some("contentLocked") => control.lock_content = true,
+            Some("sdtContentLocked") => {
+                control.lock_delete = true;
+                control.lock_content = true;
+            },
+            _ => {},
+        },
+        b"rPr" if is_property => slots.push((index, Slot::RunProperties)),
+        b"showingPlcHdr" if is_property => {
+            control.showing_placeholder = on_off(e);
+            slots.push((index, Slot::PlaceholderFlag));
+        },
+        b"dataBinding" if is_property => {
+            control.data_binding = attr(e, b"xpath").map(|xpath| DataBinding {
+                xpath,
+                store_item_id: attr(e, b"storeItemID"),
+                prefix_mappings: attr(e, b"prefixMappings").unwrap_or_default(),
+            });
+        },
+        b"text" if is_property => control.control_type = Some("text".to_string()),
+        b"richText" if is_property => control.control_type = Some("richText".to_string()),
+        b"picture" if is_property => control.control_type = Some("picture".to_string()),
+        b"date" if is_property => {
+            control.control_type = Some("date".to_string());
+            control.date = attr(e, b"fullDate")
+                .and_then(|date| NaiveDate::parse_from_str(date.get(..10)?, "%Y-%m-%d").ok());
+            frame.layout.date = Some(tag);
+        },
+        b"dateFormat" => control.date_format = attr(e, b"val"),
+        b"comboBox" | b"dropDownList" if is_property => {
+            control.control_type = Some(String::from_utf8_lossy(local).into_owned());
+            frame.layout.list = Some(tag);
+        },
+        b"listItem" => {
+            let value = attr(e, b"value").unwrap_or_default();
+            let display = attr(e, b"displayText").unwrap_or_else(|| value.clone());
+            control.list_items.push((display, value));
+        },
+        b"checkbox" if is_property => {
+            control.control_type = Some("checkbox".to_string());
+            control.checked.get_or_insert(false);
+        },
+        b"checked" => {
+            control.checked = Some(on_off(e));
+            slots.push((index, Slot::Checked));
+        },
+        b"checkedState" => frame.layout.checked_state = check_state(e),
+        b"uncheckedState" => frame.layout.unchecked_state = check_state(e),
+        _ => {},
+    }
+
+    slots
+}
+
+/// Handle an element end; the element spans `open.start..end`.
+fn close_element(
+    open: Open,
+    end: usize,
+    stack: &[Open],
+    frames: &mut Vec<Frame>,
+    controls: &mut [Option<(ContentControl, SdtLayout)>],
+) {
+    let range = open.start..end;
+    for (index, slot) in open.slots {
+        let layout = &mut frames[index].layout;
+        let target = match slot {
+            Slot::RunProperties => &mut layout.run_properties,
+            Slot::PlaceholderFlag => &mut layout.placeholder_flag,
+            Slot::Checked => &mut layout.checked,
+            Slot::Content => &mut layout.content,
+            Slot::FirstParagraphProperties => &mut layout.first_paragraph_properties,
+            Slot::FirstRunProperties => &mut layout.first_run_properties,
+        };
+        *target = Some(range.clone());
+    }
+
+    let depth = stack.len() + 1;
+    for frame in frames.iter_mut() {
+        match open.local.as_slice() {
+            b"r" if frame.run_depth == Some(depth) => {
+                frame.run_depth = None;
+                frame.first_run_done = true;
+            },
+            b"p" if frame.in_content => {
+                if frame.paragraph_depth == Some(depth) {
+                    frame.paragraph_depth = None;
+                    frame.first_paragraph_done = true;
+                }
+                frame.control.value.push('\n');
+            },
+            b"sdtPr" if depth == frame.depth + 1 => frame.in_properties = false,
+            b"sdtContent" if depth == frame.depth + 1 => frame.in_content = false,
+            _ => {},
+        }
+    }
 
-        Ok(controls)
+    if open.local == b"sdt"
+        && let Some(frame) = frames.last()
+        && frame.depth == depth
+        && let Some(mut frame) = frames.pop()
+    {
+        let trimmed = frame.control.value.trim_end_matches('\n').len();
+        frame.control.value.truncate(trimmed);
+        controls[frame.index] = Some((frame.control, frame.layout));
     }
 }
 
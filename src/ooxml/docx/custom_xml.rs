@@ -70,7 +70,7 @@ impl CustomXmlPart {
 
     /// Extract custom XML part from a part.
     pub(crate) fn from_part(part: &dyn Part, id: String) -> Result<Self> {
-        let xml_content = String::from_utf8_lossy(part.blob()).into_owned();
+        let xml_content = String::from_utf8_lossy(part.try_blob()?).into_owned();
         Ok(Self {
             id,
             xml_content,
@@ -1,4 +1,9 @@
 /// Document - the main API for working with Word document content.
+use crate::common::Language;
+use crate::common::walk::TextEvent;
+use crate::document::{ListCounter, RevisionMode};
+use crate::ooxml::charts::Chart;
+use crate::ooxml::charts::reader::read_related_charts;
 use crate::ooxml::docx::bookmark::Bookmark;
 use crate::ooxml::docx::comment::Comment;
 use crate::ooxml::docx::content_control::ContentControl;
@@ -6,11 +11,14 @@ use crate::ooxml::docx::custom_xml::CustomXmlPart;
 use crate::ooxml::docx::enums::WdHeaderFooter;
 use crate::ooxml::docx::field::Field;
 use crate::ooxml::docx::footnote::Note;
+use crate::ooxml::docx::fragment::{self, DocumentFragment};
 use crate::ooxml::docx::header_footer::HeaderFooter;
 use crate::ooxml::docx::hyperlink::Hyperlink;
 use crate::ooxml::docx::numbering::Numbering;
+use crate::ooxml::docx::ole_object::{OleObject, extract_references};
 use crate::ooxml::docx::paragraph::Paragraph;
 use crate::ooxml::docx::parts::DocumentPart;
+use crate::ooxml::docx::revision::Revision;
 use crate::ooxml::docx::section::{Section, Sections};
 use crate::ooxml::docx::settings::DocumentSettings;
 use crate::ooxml::docx::statistics::{
@@ -24,8 +32,11 @@ use crate::ooxml::docx::variables::DocumentVariables;
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::OpcPackage;
 use crate::ooxml::opc::constants::relationship_type;
+use crate::ooxml::pptx::smartart::{SmartArt, diagram_rel_ids, read_related_smartart};
 use quick_xml::Reader;
 use quick_xml::events::Event;
+use std::ops::ControlFlow;
+use std::sync::Arc;
 
 /// A Word document.
 ///
@@ -82,7 +93,62 @@ impl<'a> Document<'a> {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn text(&self) -> Result<String> {
-        self.part.extract_text()
+        self.part.extract_text_with_diagrams(&|rel_id| {
+            Ok(self.read_smartart(rel_id)?.map(|smartart| smartart.text()))
+        })
+    }
+
+    /// Report the body text to `visitor` while parsing the document part,
+    /// stopping as soon as it returns [`ControlFlow::Break`].
+    ///
+    /// See [`crate::Document::walk_text`].
+    pub fn walk_text(
+        &self,
+        visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        self.part.walk_text(
+            &|rel_id| Ok(self.read_smartart(rel_id)?.map(|smartart| smartart.text())),
+            visitor,
+        )
+    }
+
+    /// Get all text content with tracked changes materialized.
+    ///
+    /// Insertions and moved-in text are kept or dropped according to `mode`,
+    /// as are deletions and moved-away text. Paragraphs are separated by
+    /// newlines.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::document::RevisionMode;
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("document.docx")?;
+    /// let doc = pkg.document()?;
+    /// let original = doc.text_with_revisions(RevisionMode::RejectAll)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn text_with_revisions(&self, mode: RevisionMode) -> Result<String> {
+        self.part.extract_text_with_revisions(mode)
+    }
+
+    /// Get all tracked changes (revisions) in the document body.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("document.docx")?;
+    /// let doc = pkg.document()?;
+    /// for revision in doc.revisions()? {
+    ///     println!("{} by {}", revision.revision_type(), revision.author());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn revisions(&self) -> Result<Vec<Revision>> {
+        Ok(self.part.extract_revisions()?.into_vec())
     }
 
     /// Get the number of paragraphs in the document.
@@ -130,7 +196,8 @@ impl<'a> Document<'a> {
     /// Get all paragraphs in the document.
     ///
     /// Returns a vector of `Paragraph` objects representing all `<w:p>`
-    /// elements in the document body.
+    /// elements in the document body. List items carry their computed
+    /// numbering, see [`Paragraph::list_info`].
     ///
     /// # Examples
     ///
@@ -152,7 +219,55 @@ impl<'a> Document<'a> {
     /// ```
     pub fn paragraphs(&self) -> Result<Vec<Paragraph>> {
         // Convert SmallVec to Vec for API compatibility
-        Ok(self.part.paragraphs()?.into_iter().collect())
+        let mut paragraphs: Vec<Paragraph> = self.part.paragraphs()?.into_iter().collect();
+        self.apply_list_info(paragraphs.iter_mut());
+        self.apply_style_languages(paragraphs.iter_mut());
+        Ok(paragraphs)
+    }
+
+    /// Get the paragraphs with indices in `range`.
+    ///
+    /// Returns the same paragraphs as `paragraphs()[range]`, but only the
+    /// requested paragraphs are parsed, which makes reading the start of a
+    /// long document cheap. Indices past the last paragraph are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("document.docx")?;
+    /// let doc = pkg.document()?;
+    ///
+    /// for para in doc.paragraphs_range(0..20)? {
+    ///     println!("{}", para.text()?);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn paragraphs_range(&self, range: std::ops::Range<usize>) -> Result<Vec<Paragraph>> {
+        let mut paragraphs = self.part.paragraphs_range(range.clone())?;
+        self.apply_style_languages(paragraphs.iter_mut());
+        if paragraphs.is_empty() || !matches!(self.numbering(), Ok(Some(_))) {
+            return Ok(paragraphs);
+        }
+
+        // List counters run from the start of the document, so earlier
+        // paragraphs that may be list items are read for their numbering
+        let xml = self.part.xml_bytes();
+        let mut preceding: Vec<Paragraph> = self
+            .part
+            .paragraph_spans(range.start)
+            .into_iter()
+            .filter(|span| {
+                let para = &xml[span.clone()];
+                memchr::memmem::find(para, b"<w:numPr").is_some()
+                    || memchr::memmem::find(para, b"<w:pStyle").is_some()
+            })
+            .map(|span| self.part.raw_paragraph(span))
+            .collect();
+        self.apply_list_info(preceding.iter_mut().chain(paragraphs.iter_mut()));
+
+        Ok(paragraphs)
     }
 
     /// Get all tables in the document.
@@ -190,6 +305,7 @@ impl<'a> Document<'a> {
     /// returning an ordered vector that preserves the document structure.
     /// This is more efficient than calling `paragraphs()` and `tables()` separately,
     /// and it maintains the correct order of elements for sequential processing.
+    /// Top-level list items carry their computed numbering.
     ///
     /// # Examples
     ///
@@ -218,7 +334,99 @@ impl<'a> Document<'a> {
     /// Uses a single-pass XML parser that is significantly faster than
     /// calling `paragraphs()` and `tables()` separately.
     pub fn elements(&self) -> Result<Vec<crate::document::DocumentElement>> {
-        self.part.elements()
+        use crate::document::DocumentElement;
+
+        fn paragraphs(elements: &mut [DocumentElement]) -> impl Iterator<Item = &mut Paragraph> {
+            elements.iter_mut().filter_map(|element| match element {
+                DocumentElement::Paragraph(para) => match para.as_mut() {
+                    crate::document::Paragraph::Docx(para) => Some(para),
+                    #[allow(unreachable_patterns)]
+                    _ => None,
+                },
+                DocumentElement::Table(_) => None,
+            })
+        }
+
+        let mut elements = self.part.elements()?;
+        // Tables are parsed lazily, so only top-level paragraphs are numbered
+        self.apply_list_info(paragraphs(&mut elements));
+        self.apply_style_languages(paragraphs(&mut elements));
+        Ok(elements)
+    }
+
+    /// Compute the list numbering of paragraphs given in document order.
+    ///
+    /// Numbering comes from the paragraph's own `w:numPr`, or else from its
+    /// style. Documents without a numbering part are left untouched.
+    fn apply_list_info<'p>(&self, paragraphs: impl Iterator<Item = &'p mut Paragraph>) {
+        let Ok(Some(numbering)) = self.numbering() else {
+            return;
+        };
+        let mut styles = self.styles().ok();
+        let mut counter = ListCounter::new();
+
+        for para in paragraphs {
+            let mut num_id = para.num_id().ok().flatten();
+            let mut level = para.numbering_level().ok().flatten();
+            if num_id.is_none()
+                && let Some(styles) = styles.as_mut()
+                && let Some(style_id) = para.style_id().ok().flatten()
+                && let Ok(Some((style_num_id, style_level))) = styles.numbering(&style_id)
+            {
+                num_id = Some(style_num_id);
+                level = level.or(Some(style_level));
+            }
+
+            // numId 0 removes numbering inherited from the style
+            let list_info = num_id
+                .filter(|&id| id != 0)
+                .and_then(|id| numbering.level_formats(id))
+                .and_then(|(key, formats)| counter.next(key, level.unwrap_or(0), &formats));
+            para.set_list_info(list_info);
+        }
+    }
+
+    /// Attach the style languages to paragraphs so that their runs resolve
+    /// languages through styles. Nothing is attached when no style
+    /// declares a language.
+    fn apply_style_languages<'p>(&self, paragraphs: impl Iterator<Item = &'p mut Paragraph>) {
+        let Some(languages) = self
+            .styles()
+            .and_then(|mut styles| styles.languages())
+            .ok()
+            .filter(|languages| !languages.is_empty())
+        else {
+            return;
+        };
+        let languages = Arc::new(languages);
+        for para in paragraphs {
+            para.set_style_languages(Arc::clone(&languages));
+        }
+    }
+
+    /// Get the document default languages as BCP-47 tags.
+    ///
+    /// These are the languages of the document defaults (`w:docDefaults`)
+    /// and the default paragraph style, which apply to text that does not
+    /// declare its own.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("document.docx")?;
+    /// let doc = pkg.document()?;
+    /// if let Some(tag) = doc.default_language()?.latin {
+    ///     println!("Written in {}", tag);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn default_language(&self) -> Result<Language> {
+        match self.styles() {
+            Ok(mut styles) => styles.default_language(),
+            Err(_) => Ok(Language::default()),
+        }
     }
 
     /// Get all sections in the document.
@@ -670,6 +878,65 @@ impl<'a> Document<'a> {
         Ok(footers)
     }
 
+    /// Get the headers of each section, in section order.
+    ///
+    /// Unlike [`Document::headers`], the header types come from the
+    /// `<w:headerReference>` elements of each section. A section only lists
+    /// the headers it declares; sections without a reference of some type
+    /// continue the header of the previous section.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("document.docx")?;
+    /// let doc = pkg.document()?;
+    ///
+    /// for (index, headers) in doc.headers_by_section()?.iter().enumerate() {
+    ///     for (hdr_type, header) in headers {
+    ///         println!("Section {} {:?} header: {}", index, hdr_type, header.text()?);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn headers_by_section(&self) -> Result<Vec<Vec<(WdHeaderFooter, HeaderFooter)>>> {
+        self.sections()?
+            .iter()
+            .map(|section| self.resolve_header_footers(section.header_references()))
+            .collect()
+    }
+
+    /// Get the footers of each section, in section order.
+    ///
+    /// See [`Document::headers_by_section`] for details.
+    pub fn footers_by_section(&self) -> Result<Vec<Vec<(WdHeaderFooter, HeaderFooter)>>> {
+        self.sections()?
+            .iter()
+            .map(|section| self.resolve_header_footers(section.footer_references()))
+            .collect()
+    }
+
+    /// Load the header or footer parts of section references.
+    fn resolve_header_footers(
+        &self,
+        references: Vec<(WdHeaderFooter, String)>,
+    ) -> Result<Vec<(WdHeaderFooter, HeaderFooter)>> {
+        let main_part = self.opc.main_document_part()?;
+        let rels = main_part.rels();
+
+        let mut result = Vec::new();
+        for (hdr_type, r_id) in references {
+            // Skip dangling references rather than failing the whole document
+            let Some(rel) = rels.get(&r_id) else {
+                continue;
+            };
+            let part = self.opc.get_part(&rel.target_partname()?)?;
+            result.push((hdr_type, HeaderFooter::from_part(part, hdr_type)?));
+        }
+        Ok(result)
+    }
+
     /// Get a specific header by type.
     ///
     /// # Arguments
@@ -772,6 +1039,123 @@ impl<'a> Document<'a> {
         Ok(self.hyperlinks()?.len())
     }
 
+    /// Get all charts embedded in the document body.
+    ///
+    /// Series data is taken from the values cached in each chart part.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("document.docx")?;
+    /// let doc = pkg.document()?;
+    ///
+    /// for chart in doc.charts()? {
+    ///     println!("Chart with {} series", chart.plot_area.series().count());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn charts(&self) -> Result<Vec<Chart>> {
+        let main_part = self.opc.main_document_part()?;
+        read_related_charts(self.opc, main_part)
+    }
+
+    /// Get all SmartArt diagrams in the document body, in document order.
+    ///
+    /// Their text is part of [`Document::text`], one node per line.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("document.docx")?;
+    /// let doc = pkg.document()?;
+    ///
+    /// for smartart in doc.smartart()? {
+    ///     for node in smartart.nodes() {
+    ///         println!("{} ({} children)", node.text, node.children.len());
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn smartart(&self) -> Result<Vec<SmartArt>> {
+        let mut diagrams = Vec::new();
+        for rel_id in diagram_rel_ids(self.part.xml_bytes())? {
+            if let Some(smartart) = self.read_smartart(&rel_id)? {
+                diagrams.push(smartart);
+            }
+        }
+        Ok(diagrams)
+    }
+
+    /// Whether the document body may hold SmartArt diagrams, a cheap check
+    /// done before parsing paragraphs for them.
+    pub(crate) fn may_have_smartart(&self) -> bool {
+        memchr::memmem::find(self.part.xml_bytes(), b"relIds").is_some()
+    }
+
+    /// Read the SmartArt diagram whose data part is the target of `rel_id`.
+    pub(crate) fn read_smartart(&self, rel_id: &str) -> Result<Option<SmartArt>> {
+        let main_part = self.opc.main_document_part()?;
+        read_related_smartart(self.opc, main_part, rel_id)
+    }
+
+    /// Get all OLE objects embedded in the document body.
+    ///
+    /// Linked objects and objects whose part is missing are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("document.docx")?;
+    /// let doc = pkg.document()?;
+    ///
+    /// for object in doc.ole_objects()? {
+    ///     println!("{}: {}", object.partname(), object.prog_id().unwrap_or("unknown"));
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn ole_objects(&self) -> Result<Vec<OleObject>> {
+        let main_part = self.opc.main_document_part()?;
+        let rels = main_part.rels();
+
+        // Resolve a relationship to its part, ignoring external targets
+        let resolve = |r_id: &str| -> Result<Option<(String, Vec<u8>)>> {
+            let Some(rel) = rels.get(r_id).filter(|rel| !rel.is_external()) else {
+                return Ok(None);
+            };
+            let partname = rel.target_partname()?;
+            let Ok(part) = self.opc.get_part(&partname) else {
+                return Ok(None);
+            };
+            Ok(Some((partname.to_string(), part.try_blob()?.to_vec())))
+        };
+
+        let mut objects = Vec::new();
+        for reference in extract_references(self.part.xml_bytes())? {
+            let Some((partname, data)) = resolve(&reference.r_id)? else {
+                continue;
+            };
+            let preview = match &reference.preview_r_id {
+                Some(r_id) => resolve(r_id)?,
+                None => None,
+            };
+            let (preview_partname, preview) = preview.unzip();
+            objects.push(OleObject::new(
+                reference.prog_id,
+                partname,
+                data,
+                preview_partname,
+                preview,
+            ));
+        }
+        Ok(objects)
+    }
+
     /// Get all footnotes in the document.
     ///
     /// Returns a vector of `Note` objects representing all footnotes
@@ -971,6 +1355,32 @@ impl<'a> Document<'a> {
         Ok(self.bookmarks()?.len())
     }
 
+    /// Copy the content of the bookmark `name`.
+    ///
+    /// The fragment holds the bookmarked paragraphs, partial paragraphs,
+    /// tables and images together with the styles, numbering definitions
+    /// and parts they use, ready to be inserted into another document with
+    /// [`MutableDocument::insert_fragment_at_bookmark`].
+    ///
+    /// Returns an error if the bookmark does not exist.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let pkg = Package::open("clauses.docx")?;
+    /// let fragment = pkg.document()?.extract_bookmark_content("liability")?;
+    /// println!("{}", fragment.text()?);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    ///
+    /// [`MutableDocument::insert_fragment_at_bookmark`]: crate::ooxml::docx::writer::MutableDocument::insert_fragment_at_bookmark
+    pub fn extract_bookmark_content(&self, name: &str) -> Result<DocumentFragment> {
+        let main_part = self.opc.main_document_part()?;
+        fragment::extract(self.opc, main_part, name)
+    }
+
     /// Get all fields in the document.
     ///
     /// Returns a vector of `Field` objects representing all fields
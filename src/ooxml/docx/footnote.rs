@@ -296,7 +296,7 @@ impl Note {
 
     /// Extract notes from a part (generic for footnotes and endnotes).
     fn extract_notes_from_part(part: &dyn Part, note_tag: &[u8]) -> Result<Vec<Note>> {
-        let xml_bytes = part.blob();
+        let xml_bytes = part.try_blob()?;
         let mut reader = Reader::from_reader(xml_bytes);
         reader.config_mut().trim_text(true);
 
@@ -0,0 +1,724 @@
+/// Bookmarked content copied out of a Word document.
+///
+/// A [`DocumentFragment`] holds the content between the start and the end of
+/// a bookmark together with everything it refers to: the style definitions,
+/// the numbering definitions, and the parts behind its relationships
+/// (images, hyperlinks, charts). It can be inserted into another document
+/// with [`MutableDocument::insert_fragment_at_bookmark`].
+///
+/// [`MutableDocument::insert_fragment_at_bookmark`]: crate::ooxml::docx::writer::MutableDocument::insert_fragment_at_bookmark
+use crate::common::xml::attr;
+use crate::ooxml::docx::content_control::push_general_ref;
+use crate::ooxml::error::{OoxmlError, Result};
+use crate::ooxml::opc::OpcPackage;
+use crate::ooxml::opc::constants::relationship_type;
+use crate::ooxml::opc::packuri::PackURI;
+use crate::ooxml::opc::part::Part;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Content copied from a bookmarked range of a document.
+///
+/// Obtained with [`Document::extract_bookmark_content`]. A fragment is
+/// either inline (runs from within one paragraph) or a sequence of block
+/// elements (paragraphs and tables); paragraphs the bookmark only partly
+/// covers keep their properties and the selected runs.
+///
+/// Comments, footnotes and endnotes are not carried over; their
+/// references are left out of the fragment.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::ooxml::docx::Package;
+/// use litchi::ooxml::docx::writer::FragmentPosition;
+///
+/// let source = Package::open("clauses.docx")?;
+/// let fragment = source.document()?.extract_bookmark_content("liability")?;
+///
+/// let mut pkg = Package::open("contract.docx")?;
+/// pkg.document_mut()?
+///     .insert_fragment_at_bookmark("clauses", fragment, FragmentPosition::Replace)?;
+/// pkg.save("contract_out.docx")?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`Document::extract_bookmark_content`]: crate::ooxml::docx::Document::extract_bookmark_content
+#[derive(Debug, Clone)]
+pub struct DocumentFragment {
+    /// Content XML: runs for inline fragments, block elements otherwise
+    pub(crate) xml: String,
+    /// Whether the content is made of runs rather than blocks
+    pub(crate) inline: bool,
+    /// Namespace declarations of the source document (prefix, URI)
+    pub(crate) namespaces: Vec<(String, String)>,
+    /// Style definitions used by the content
+    pub(crate) styles: Vec<FragmentStyle>,
+    /// Abstract numbering definitions used by the content
+    pub(crate) abstract_nums: Vec<FragmentAbstractNum>,
+    /// Numbering instances used by the content
+    pub(crate) nums: Vec<FragmentNum>,
+    /// Relationships of the source document part used by the content
+    pub(crate) relationships: Vec<FragmentRelationship>,
+    /// Parts the relationships point to
+    pub(crate) parts: Vec<FragmentPart>,
+    /// Whether source styles replace destination styles with the same name
+    pub(crate) force_styles: bool,
+}
+
+/// A `<w:style>` definition carried by a fragment.
+#[derive(Debug, Clone)]
+pub(crate) struct FragmentStyle {
+    pub(crate) id: String,
+    pub(crate) name: Option<String>,
+    pub(crate) xml: String,
+}
+
+/// A `<w:abstractNum>` definition carried by a fragment.
+#[derive(Debug, Clone)]
+pub(crate) struct FragmentAbstractNum {
+    pub(crate) id: String,
+    pub(crate) xml: String,
+}
+
+/// A `<w:num>` instance carried by a fragment.
+#[derive(Debug, Clone)]
+pub(crate) struct FragmentNum {
+    pub(crate) id: String,
+    pub(crate) xml: String,
+}
+
+/// A relationship carried by a fragment.
+#[derive(Debug, Clone)]
+pub(crate) struct FragmentRelationship {
+    pub(crate) r_id: String,
+    pub(crate) reltype: String,
+    pub(crate) target: FragmentTarget,
+}
+
+/// The target of a relationship carried by a fragment.
+#[derive(Debug, Clone)]
+pub(crate) enum FragmentTarget {
+    /// An external URL
+    External(String),
+    /// An index into the parts of the fragment
+    Part(usize),
+}
+
+/// A part copied along with a fragment.
+#[derive(Debug, Clone)]
+pub(crate) struct FragmentPart {
+    /// Partname in the source package
+    pub(crate) partname: PackURI,
+    pub(crate) content_type: String,
+    pub(crate) blob: Vec<u8>,
+    pub(crate) relationships: Vec<FragmentRelationship>,
+}
+
+impl DocumentFragment {
+    /// Check whether the fragment is made of runs from a single paragraph.
+    ///
+    /// Inline fragments are inserted into the paragraph holding the target
+    /// bookmark; block fragments split it.
+    #[inline]
+    pub fn is_inline(&self) -> bool {
+        self.inline
+    }
+
+    /// Get the WordprocessingML content of the fragment.
+    #[inline]
+    pub fn xml(&self) -> &str {
+        &self.xml
+    }
+
+    /// Get the text of the fragment, with paragraphs separated by newlines.
+    pub fn text(&self) -> Result<String> {
+        let mut reader = Reader::from_str(&self.xml);
+        let mut text = String::new();
+        let mut in_text = false;
+        loop {
+            match reader
+                .read_event()
+                .map_err(|e| OoxmlError::Xml(e.to_string()))?
+            {
+                Event::Start(e) if e.local_name().as_ref() == b"t" => in_text = true,
+                Event::End(e) => match e.local_name().as_ref() {
+                    b"t" => in_text = false,
+                    b"p" => text.push('\n'),
+                    _ => {},
+                },
+                Event::Empty(e) => match e.local_name().as_ref() {
+                    b"tab" => text.push('\t'),
+                    b"br" | b"cr" => text.push('\n'),
+                    _ => {},
+                },
+                Event::Text(e) if in_text => {
+                    text.push_str(&e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?)
+                },
+                Event::GeneralRef(e) if in_text => push_general_ref(&mut text, &e)?,
+                Event::Eof => break,
+                _ => {},
+            }
+        }
+        if text.ends_with('\n') {
+            text.pop();
+        }
+        Ok(text)
+    }
+
+    /// Get the IDs of the styles carried by the fragment.
+    pub fn style_ids(&self) -> impl Iterator<Item = &str> {
+        self.styles.iter().map(|style| style.id.as_str())
+    }
+
+    /// Get the number of parts (images, charts, ...) carried by the fragment.
+    #[inline]
+    pub fn part_count(&self) -> usize {
+        self.parts.len()
+    }
+
+    /// Let the fragment's style definitions replace destination styles of
+    /// the same name on insertion.
+    ///
+    /// By default a destination style with the same name wins and the
+    /// fragment content is restyled with it.
+    pub fn with_source_styles(mut self, force: bool) -> Self {
+        self.force_styles = force;
+        self
+    }
+}
+
+/// An element of an XML part with its byte ranges.
+#[derive(Debug)]
+pub(crate) struct Node {
+    /// Qualified element name
+    pub(crate) name: Vec<u8>,
+    /// The whole element
+    pub(crate) range: Range<usize>,
+    /// The start tag (the whole element for empty elements)
+    pub(crate) start_tag: Range<usize>,
+    pub(crate) children: Vec<Node>,
+}
+
+impl Node {
+    /// Get the local name of the element.
+    pub(crate) fn local(&self) -> &[u8] {
+        match self.name.iter().position(|&b| b == b':') {
+            Some(colon) => &self.name[colon + 1..],
+            None => &self.name,
+        }
+    }
+
+    /// Get the unescaped value of the attribute with local name `name`.
+    pub(crate) fn attr(&self, xml: &[u8], name: &[u8]) -> Option<String> {
+        let mut reader = Reader::from_reader(&xml[self.start_tag.clone()]);
+        match reader.read_event().ok()? {
+            Event::Start(e) | Event::Empty(e) => attr(&e, name),
+            _ => None,
+        }
+    }
+
+    /// Get the value of the `w:val` attribute of the first child named `name`.
+    pub(crate) fn child_val(&self, xml: &[u8], name: &[u8]) -> Option<String> {
+        self.children
+            .iter()
+            .find(|child| child.local() == name)
+            .and_then(|child| child.attr(xml, b"val"))
+    }
+
+    /// Get the node at `path`, a list of child indexes.
+    pub(crate) fn at(&self, path: &[usize]) -> &Node {
+        path.iter().fold(self, |node, &i| &node.children[i])
+    }
+
+    /// Find the path to the first element, in document order, matching
+    /// `predicate`.
+    pub(crate) fn find_path(&self, predicate: &impl Fn(&Node) -> bool) -> Option<Vec<usize>> {
+        for (i, child) in self.children.iter().enumerate() {
+            if predicate(child) {
+                return Some(vec![i]);
+            }
+            if let Some(mut path) = child.find_path(predicate) {
+                path.insert(0, i);
+                return Some(path);
+            }
+        }
+        None
+    }
+
+    /// Visit the element and its descendants in document order.
+    pub(crate) fn walk<'n>(&'n self, f: &mut impl FnMut(&'n Node)) {
+        f(self);
+        for child in &self.children {
+            child.walk(f);
+        }
+    }
+
+    /// Check whether the element holds blocks (paragraphs and tables).
+    pub(crate) fn is_block_container(&self) -> bool {
+        matches!(self.local(), b"body" | b"tc" | b"txbxContent")
+            || self
+                .children
+                .iter()
+                .any(|child| matches!(child.local(), b"p" | b"tbl"))
+    }
+}
+
+/// Parse the element tree of an XML part.
+pub(crate) fn parse_tree(xml: &[u8]) -> Result<Node> {
+    let mut reader = Reader::from_reader(xml);
+    let mut stack: Vec<Node> = Vec::new();
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader
+            .read_event()
+            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        let end = reader.buffer_position() as usize;
+        match event {
+            Event::Start(e) => stack.push(Node {
+                name: e.name().as_ref().to_vec(),
+                range: start..end,
+                start_tag: start..end,
+                children: Vec::new(),
+            }),
+            Event::Empty(e) => {
+                let node = Node {
+                    name: e.name().as_ref().to_vec(),
+                    range: start..end,
+                    start_tag: start..end,
+                    children: Vec::new(),
+                };
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => return Ok(node),
+                }
+            },
+            Event::End(_) => {
+                let Some(mut node) = stack.pop() else {
+                    return Err(OoxmlError::Xml("unexpected end tag".to_string()));
+                };
+                node.range.end = end;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => return Ok(node),
+                }
+            },
+            Event::Eof => return Err(OoxmlError::Xml("missing root element".to_string())),
+            _ => {},
+        }
+    }
+}
+
+/// Elements left out of fragments because they refer to content that is
+/// not carried over.
+const DROPPED_ELEMENTS: &[&[u8]] = &[
+    b"commentRangeStart",
+    b"commentRangeEnd",
+    b"commentReference",
+    b"footnoteReference",
+    b"endnoteReference",
+];
+
+/// Paragraph children that mark positions rather than hold content.
+pub(crate) const MARKER_ELEMENTS: &[&[u8]] = &[
+    b"pPr",
+    b"bookmarkStart",
+    b"bookmarkEnd",
+    b"proofErr",
+    b"permStart",
+    b"permEnd",
+    b"commentRangeStart",
+    b"commentRangeEnd",
+];
+
+/// Copy `node` to `out`, leaving out dropped elements and section
+/// properties of paragraphs.
+fn copy_node(xml: &[u8], node: &Node, parent: &[u8], out: &mut Vec<u8>) {
+    if DROPPED_ELEMENTS.contains(&node.local()) || (node.local() == b"sectPr" && parent == b"pPr") {
+        return;
+    }
+    let Some(first) = node.children.first() else {
+        out.extend_from_slice(&xml[node.range.clone()]);
+        return;
+    };
+    out.extend_from_slice(&xml[node.range.start..first.range.start]);
+    let mut position = first.range.start;
+    for child in &node.children {
+        out.extend_from_slice(&xml[position..child.range.start]);
+        copy_node(xml, child, node.local(), out);
+        position = child.range.end;
+    }
+    out.extend_from_slice(&xml[position..node.range.end]);
+}
+
+/// Copy a paragraph keeping only its properties and `children`.
+fn copy_partial_paragraph(xml: &[u8], paragraph: &Node, children: &[Node], out: &mut Vec<u8>) {
+    out.extend_from_slice(&xml[paragraph.start_tag.clone()]);
+    if let Some(properties) = paragraph.children.first().filter(|c| c.local() == b"pPr") {
+        copy_node(xml, properties, b"p", out);
+    }
+    for child in children.iter().filter(|c| c.local() != b"pPr") {
+        copy_node(xml, child, b"p", out);
+    }
+    out.extend_from_slice(b"</");
+    out.extend_from_slice(&paragraph.name);
+    out.push(b'>');
+}
+
+/// Check whether paragraph children hold content other than markers.
+pub(crate) fn has_content(children: &[Node]) -> bool {
+    children
+        .iter()
+        .any(|child| !MARKER_ELEMENTS.contains(&child.local()))
+}
+
+/// Find the paths to the start and end of the bookmark `name`.
+pub(crate) fn find_bookmark(
+    xml: &[u8],
+    root: &Node,
+    name: &str,
+) -> Result<(Vec<usize>, Vec<usize>)> {
+    let start = root
+        .find_path(&|node| {
+            node.local() == b"bookmarkStart" && node.attr(xml, b"name").as_deref() == Some(name)
+        })
+        .ok_or_else(|| OoxmlError::InvalidFormat(format!("Bookmark '{}' not found", name)))?;
+    let id = root.at(&start).attr(xml, b"id");
+    let end = root
+        .find_path(&|node| node.local() == b"bookmarkEnd" && node.attr(xml, b"id") == id)
+        .ok_or_else(|| OoxmlError::InvalidFormat(format!("Bookmark '{}' has no end", name)))?;
+    if end < start {
+        return Err(OoxmlError::InvalidFormat(format!(
+            "Bookmark '{}' ends before it starts",
+            name
+        )));
+    }
+    Ok((start, end))
+}
+
+/// Extract the content of the bookmark `name` from the document part `part`.
+pub(crate) fn extract(opc: &OpcPackage, part: &dyn Part, name: &str) -> Result<DocumentFragment> {
+    let xml = part.try_blob()?;
+    let root = parse_tree(xml)?;
+    let (start, end) = find_bookmark(xml, &root, name)?;
+
+    let common = start.iter().zip(&end).take_while(|(a, b)| a == b).count();
+    let container = root.at(&start[..common]);
+    let (first, last) = (start[common], end[common]);
+    let mut content = Vec::new();
+    let inline = !container.is_block_container();
+    if inline {
+        for child in &container.children[first + 1..last] {
+            copy_node(xml, child, container.local(), &mut content);
+        }
+    } else {
+        // The paragraph holding the start only contributes what follows it
+        let first_node = &container.children[first];
+        if start.len() == common + 2 && first_node.local() == b"p" {
+            let children = &first_node.children[start[common + 1] + 1..];
+            copy_partial_paragraph(xml, first_node, children, &mut content);
+        } else if start.len() > common + 1 {
+            copy_node(xml, first_node, container.local(), &mut content);
+        }
+
+        for child in &container.children[first + 1..last] {
+            if child.local() != b"sectPr" {
+                copy_node(xml, child, container.local(), &mut content);
+            }
+        }
+
+        // And the paragraph holding the end what precedes it
+        let last_node = &container.children[last];
+        if end.len() == common + 2 && last_node.local() == b"p" {
+            let children = &last_node.children[..end[common + 1]];
+            if has_content(children) {
+                copy_partial_paragraph(xml, last_node, children, &mut content);
+            }
+        } else if end.len() > common + 1 {
+            copy_node(xml, last_node, container.local(), &mut content);
+        }
+    }
+    let content = String::from_utf8(content).map_err(|e| OoxmlError::Xml(e.to_string()))?;
+
+    let mut namespaces = Vec::new();
+    let mut reader = Reader::from_reader(&xml[root.start_tag.clone()]);
+    if let Ok(Event::Start(e)) = reader.read_event() {
+        for attr in e.attributes().flatten() {
+            if let Some(prefix) = attr.key.as_ref().strip_prefix(b"xmlns:") {
+                namespaces.push((
+                    String::from_utf8_lossy(prefix).into_owned(),
+                    String::from_utf8_lossy(&attr.value).into_owned(),
+                ));
+            }
+        }
+    }
+
+    let references = References::collect(content.as_bytes())?;
+
+    // Styles, with the styles they are based on or linked to
+    let mut styles = Vec::new();
+    let mut num_ids = references.num_ids.clone();
+    if let Some(styles_xml) = related_part(opc, part, relationship_type::STYLES)? {
+        let styles_root = parse_tree(styles_xml)?;
+        let definitions: HashMap<String, &Node> = styles_root
+            .children
+            .iter()
+            .filter(|node| node.local() == b"style")
+            .filter_map(|node| Some((node.attr(styles_xml, b"styleId")?, node)))
+            .collect();
+        let mut pending = references.style_ids.clone();
+        let mut seen = Vec::new();
+        while let Some(id) = pending.pop() {
+            if seen.contains(&id) {
+                continue;
+            }
+            seen.push(id.clone());
+            let Some(node) = definitions.get(&id) else {
+                continue;
+            };
+            pending.extend(
+                [b"basedOn".as_slice(), b"link"]
+                    .iter()
+                    .filter_map(|name| node.child_val(styles_xml, name)),
+            );
+            let style_xml = String::from_utf8_lossy(&styles_xml[node.range.clone()]).into_owned();
+            num_ids.extend(References::collect(style_xml.as_bytes())?.num_ids);
+            styles.push((
+                node.range.start,
+                FragmentStyle {
+                    id,
+                    name: node.child_val(styles_xml, b"name"),
+                    xml: style_xml,
+                },
+            ));
+        }
+        styles.sort_by_key(|(position, _)| *position);
+    }
+    let styles = styles.into_iter().map(|(_, style)| style).collect();
+
+    // Numbering instances and their abstract definitions
+    let mut abstract_nums: Vec<FragmentAbstractNum> = Vec::new();
+    let mut nums: Vec<FragmentNum> = Vec::new();
+    if let Some(numbering_xml) = related_part(opc, part, relationship_type::NUMBERING)? {
+        let numbering_root = parse_tree(numbering_xml)?;
+        for num_id in num_ids {
+            if num_id == "0" || nums.iter().any(|num| num.id == num_id) {
+                continue;
+            }
+            let Some(num) = numbering_root.children.iter().find(|node| {
+                node.local() == b"num"
+                    && node.attr(numbering_xml, b"numId").as_deref() == Some(num_id.as_str())
+            }) else {
+                continue;
+            };
+            let Some(abstract_id) = num.child_val(numbering_xml, b"abstractNumId") else {
+                continue;
+            };
+            if !abstract_nums.iter().any(|a| a.id == abstract_id)
+                && let Some(node) = numbering_root.children.iter().find(|node| {
+                    node.local() == b"abstractNum"
+                        && node.attr(numbering_xml, b"abstractNumId").as_deref()
+                            == Some(abstract_id.as_str())
+                })
+            {
+                abstract_nums.push(FragmentAbstractNum {
+                    id: abstract_id,
+                    xml: String::from_utf8_lossy(&numbering_xml[node.range.clone()]).into_owned(),
+                });
+            }
+            nums.push(FragmentNum {
+                id: num_id,
+                xml: String::from_utf8_lossy(&numbering_xml[num.range.clone()]).into_owned(),
+            });
+        }
+    }
+
+    // Relationships, with copies of the parts they point to
+    let mut parts = Vec::new();
+    let mut copied = HashMap::new();
+    let mut relationships = Vec::new();
+    for r_id in &references.r_ids {
+        let Some(rel) = part.rels().get(r_id) else {
+            continue;
+        };
+        if let Some(target) = copy_target(opc, rel, &mut parts, &mut copied)? {
+            relationships.push(FragmentRelationship {
+                r_id: r_id.clone(),
+                reltype: rel.reltype().to_string(),
+                target,
+            });
+        }
+    }
+
+    Ok(DocumentFragment {
+        xml: content,
+        inline,
+        namespaces,
+        styles,
+        abstract_nums,
+        nums,
+        relationships,
+        parts,
+        force_styles: false,
+    })
+}
+
+/// Get the blob of the part related to `part` with `reltype`, if there is one.
+fn related_part<'p>(
+    opc: &'p OpcPackage,
+    part: &dyn Part,
+    reltype: &str,
+) -> Result<Option<&'p [u8]>> {
+    let Some(partname) = part
+        .rels()
+        .part_with_reltype(reltype)
+        .ok()
+        .and_then(|rel| rel.target_partname().ok())
+    else {
+        return Ok(None);
+    };
+    match opc.get_part(&partname) {
+        Ok(related) => Ok(Some(related.try_blob()?)),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Get the target of a relationship, copying internal targets and the parts
+/// they relate to into `parts`.
+fn copy_target(
+    opc: &OpcPackage,
+    rel: &crate::ooxml::opc::rel::Relationship,
+    parts: &mut Vec<FragmentPart>,
+    copied: &mut HashMap<PackURI, usize>,
+) -> Result<Option<FragmentTarget>> {
+    if rel.is_external() {
+        return Ok(Some(FragmentTarget::External(rel.target_ref().to_string())));
+    }
+    let partname = rel.target_partname()?;
+    if let Some(&index) = copied.get(&partname) {
+        return Ok(Some(FragmentTarget::Part(index)));
+    }
+    let Ok(target) = opc.get_part(&partname) else {
+        return Ok(None);
+    };
+    let index = parts.len();
+    copied.insert(partname.clone(), index);
+    parts.push(FragmentPart {
+        partname,
+        content_type: target.content_type().to_string(),
+        blob: target.try_blob()?.to_vec(),
+        relationships: Vec::new(),
+    });
+    let mut relationships = Vec::new();
+    for rel in target.rels().iter() {
+        if let Some(target) = copy_target(opc, rel, parts, copied)? {
+            relationships.push(FragmentRelationship {
+                r_id: rel.r_id().to_string(),
+                reltype: rel.reltype().to_string(),
+                target,
+            });
+        }
+    }
+    parts[index].relationships = relationships;
+    Ok(Some(FragmentTarget::Part(index)))
+}
+
+/// Style, numbering and relationship IDs referred to by content XML.
+#[derive(Default)]
+struct References {
+    style_ids: Vec<String>,
+    num_ids: Vec<String>,
+    r_ids: Vec<String>,
+}
+
+impl References {
+    fn collect(xml: &[u8]) -> Result<Self> {
+        let mut references = Self::default();
+        let mut reader = Reader::from_reader(xml);
+        loop {
+            match reader
+                .read_event()
+                .map_err(|e| OoxmlError::Xml(e.to_string()))?
+            {
+                Event::Start(e) | Event::Empty(e) => {
+                    let target = match e.local_name().as_ref() {
+                        b"pStyle" | b"rStyle" | b"tblStyle" => Some(&mut references.style_ids),
+                        b"numId" => Some(&mut references.num_ids),
+                        _ => None,
+                    };
+                    if let Some(target) = target
+                        && let Some(value) = attr(&e, b"val")
+                        && !target.contains(&value)
+                    {
+                        target.push(value);
+                    }
+                    for attr in e.attributes().flatten() {
+                        if is_relationship_attr(attr.key.as_ref()) {
+                            let value = String::from_utf8_lossy(&attr.value).into_owned();
+                            if !references.r_ids.contains(&value) {
+                                references.r_ids.push(value);
+                            }
+                        }
+                    }
+                },
+                Event::Eof => break,
+                _ => {},
+            }
+        }
+        Ok(references)
+    }
+}
+
+/// Check whether an attribute holds a relationship ID (`r:id`, `r:embed`, ...).
+pub(crate) fn is_relationship_attr(key: &[u8]) -> bool {
+    key.starts_with(b"r:")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tree_ranges() {
+        let xml = br#"<w:body><w:p><w:r><w:t>a</w:t></w:r></w:p><w:sectPr/></w:body>"#;
+        let root = parse_tree(xml).unwrap();
+        assert_eq!(root.local(), b"body");
+        assert_eq!(root.children.len(), 2);
+        let paragraph = &root.children[0];
+        assert_eq!(
+            &xml[paragraph.range.clone()],
+            br#"<w:p><w:r><w:t>a</w:t></w:r></w:p>"#
+        );
+        assert_eq!(&xml[root.children[1].range.clone()], b"<w:sectPr/>");
+    }
+
+    #[test]
+    fn test_find_bookmark_and_partial_paragraph() {
+        let xml = br#"<w:body><w:p><w:pPr><w:pStyle w:val="Body"/></w:pPr><w:r><w:t>skip</w:t></w:r><w:bookmarkStart w:id="3" w:name="b"/><w:r><w:t>keep</w:t></w:r><w:commentReference w:id="1"/></w:p><w:p><w:bookmarkEnd w:id="3"/></w:p></w:body>"#;
+        let root = parse_tree(xml).unwrap();
+        let (start, end) = find_bookmark(xml, &root, "b").unwrap();
+        assert_eq!(start, vec![0, 2]);
+        assert_eq!(end, vec![1, 0]);
+        assert!(find_bookmark(xml, &root, "missing").is_err());
+
+        let paragraph = root.at(&[0]);
+        let mut out = Vec::new();
+        copy_partial_paragraph(xml, paragraph, &paragraph.children[3..], &mut out);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            r#"<w:p><w:pPr><w:pStyle w:val="Body"/></w:pPr><w:r><w:t>keep</w:t></w:r></w:p>"#
+        );
+        assert!(!has_content(&root.at(&[1]).children));
+    }
+
+    #[test]
+    fn test_references() {
+        let xml = br#"<w:p><w:pPr><w:pStyle w:val="Quote"/><w:numPr><w:numId w:val="4"/></w:numPr></w:pPr><w:hyperlink r:id="rId7"><w:r><w:rPr><w:rStyle w:val="Link"/></w:rPr></w:r></w:hyperlink><w:r><w:drawing><a:blip r:embed="rId9"/></w:drawing></w:r></w:p>"#;
+        let references = References::collect(xml).unwrap();
+        assert_eq!(references.style_ids, vec!["Quote", "Link"]);
+        assert_eq!(references.num_ids, vec!["4"]);
+        assert_eq!(references.r_ids, vec!["rId7", "rId9"]);
+    }
+}
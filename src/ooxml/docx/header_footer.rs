@@ -54,7 +54,7 @@ impl HeaderFooter {
     /// * `hdr_ftr_type` - The type of header/footer
     pub fn from_part(part: &dyn Part, hdr_ftr_type: WdHeaderFooter) -> Result<Self> {
         Ok(Self {
-            xml_bytes: part.blob().to_vec(),
+            xml_bytes: part.try_blob()?.to_vec(),
             hdr_ftr_type,
         })
     }
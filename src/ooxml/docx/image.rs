@@ -44,7 +44,8 @@ use std::borrow::Cow;
 
 /// An inline image embedded in a Word document.
 ///
-/// Represents an image within a `<w:drawing>` element. The image contains
+/// Represents an image within a `<w:drawing>` element, either inline
+/// (`<wp:inline>`) or floating (`<wp:anchor>`). The image contains
 /// metadata such as dimensions and description, with the actual binary data
 /// stored in a related media part.
 ///
@@ -66,9 +67,12 @@ pub struct InlineImage {
     /// Image description/alt text
     description: String,
 
-    /// Image name/title
+    /// Image name
     name: String,
 
+    /// Alt text title (`title` attribute of `wp:docPr`)
+    title: String,
+
     /// Width in EMUs (English Metric Units, 1 inch = 914400 EMUs)
     width_emu: i64,
 
@@ -87,7 +91,7 @@ impl InlineImage {
     /// * `width_emu` - Width in EMUs
     /// * `height_emu` - Height in EMUs
     /// * `description` - Image description/alt text
-    /// * `name` - Image name/title
+    /// * `name` - Image name
     #[inline]
     pub fn new(
         r_embed: String,
@@ -102,9 +106,17 @@ impl InlineImage {
             height_emu,
             description,
             name,
+            title: String::new(),
         }
     }
 
+    /// Set the alt text title.
+    #[inline]
+    pub fn with_title(mut self, title: String) -> Self {
+        self.title = title;
+        self
+    }
+
     /// Get the relationship ID for this image.
     #[inline]
     pub fn r_embed(&self) -> &str {
@@ -155,12 +167,21 @@ impl InlineImage {
         &self.description
     }
 
-    /// Get the image name/title.
+    /// Get the image name.
     #[inline]
     pub fn name(&self) -> &str {
         &self.name
     }
 
+    /// Get the alt text title.
+    ///
+    /// Word stores it next to the description in the "Alt Text" dialog;
+    /// it is empty for most images.
+    #[inline]
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
     /// Load the binary image data from the package.
     ///
     /// This resolves the relationship ID and loads the actual image bytes
@@ -192,7 +213,7 @@ impl InlineImage {
             .map_err(|e| OoxmlError::InvalidFormat(format!("Image part not found: {}", e)))?;
 
         // Return the binary data as a borrowed slice (zero-copy)
-        Ok(Cow::Borrowed(part.blob()))
+        Ok(Cow::Borrowed(part.try_blob()?))
     }
 
     /// Detect the image format from binary data.
@@ -208,10 +229,10 @@ impl InlineImage {
     }
 }
 
-/// Parse inline images from paragraph XML.
+/// Parse images from paragraph XML.
 ///
-/// Extracts all `<w:drawing>` elements containing `<wp:inline>` images
-/// from the paragraph XML.
+/// Extracts all `<w:drawing>` elements containing `<wp:inline>` or
+/// `<wp:anchor>` images from the paragraph XML.
 ///
 /// # Arguments
 ///
@@ -259,6 +280,7 @@ pub(crate) fn parse_inline_images(xml_bytes: &[u8]) -> Result<SmallVec<[InlineIm
     let mut height_emu: i64 = 914400; // Default 1 inch
     let mut description = String::new();
     let mut name = String::new();
+    let mut title = String::new();
     let mut r_embed = String::new();
 
     loop {
@@ -271,13 +293,14 @@ pub(crate) fn parse_inline_images(xml_bytes: &[u8]) -> Result<SmallVec<[InlineIm
                     b"drawing" => {
                         in_drawing = true;
                     },
-                    b"inline" if in_drawing => {
+                    b"inline" | b"anchor" if in_drawing => {
                         in_inline = true;
                         // Reset state for new image
                         width_emu = 914400;
                         height_emu = 914400;
                         description.clear();
                         name.clear();
+                        title.clear();
                         r_embed.clear();
                     },
                     b"extent" if in_inline => {
@@ -300,21 +323,17 @@ pub(crate) fn parse_inline_images(xml_bytes: &[u8]) -> Result<SmallVec<[InlineIm
                         }
                     },
                     b"docPr" if in_inline => {
-                        // Parse name and description from docPr element
-                        // <wp:docPr id="1" name="Picture" descr="Description"/>
+                        // Parse name, description and title from docPr element
+                        // <wp:docPr id="1" name="Picture" descr="Description" title="Title"/>
                         for attr in e.attributes().flatten() {
-                            match attr.key.as_ref() {
-                                b"name" => {
-                                    if let Ok(s) = std::str::from_utf8(&attr.value) {
-                                        name = s.to_string();
-                                    }
-                                },
-                                b"descr" => {
-                                    if let Ok(s) = std::str::from_utf8(&attr.value) {
-                                        description = s.to_string();
-                                    }
-                                },
-                                _ => {},
+                            let target = match attr.key.as_ref() {
+                                b"name" => &mut name,
+                                b"descr" => &mut description,
+                                b"title" => &mut title,
+                                _ => continue,
+                            };
+                            if let Ok(value) = attr.unescape_value() {
+                                *target = value.into_owned();
                             }
                         }
                     },
@@ -345,19 +364,22 @@ pub(crate) fn parse_inline_images(xml_bytes: &[u8]) -> Result<SmallVec<[InlineIm
                     b"drawing" => {
                         in_drawing = false;
                     },
-                    b"inline" if in_inline => {
-                        // Finished parsing an inline image
+                    b"inline" | b"anchor" if in_inline => {
+                        // Finished parsing an image
                         in_inline = false;
 
                         // Only add if we found a valid r:embed
                         if !r_embed.is_empty() {
-                            images.push(InlineImage::new(
-                                r_embed.clone(),
-                                width_emu,
-                                height_emu,
-                                description.clone(),
-                                name.clone(),
-                            ));
+                            images.push(
+                                InlineImage::new(
+                                    r_embed.clone(),
+                                    width_emu,
+                                    height_emu,
+                                    description.clone(),
+                                    name.clone(),
+                                )
+                                .with_title(title.clone()),
+                            );
                         }
                     },
                     b"blipFill" => {
@@ -465,4 +487,27 @@ mod tests {
         assert_eq!(images[0].r_embed(), "rId1");
         assert_eq!(images[1].r_embed(), "rId2");
     }
+
+    #[test]
+    fn test_parse_anchored_image() {
+        let xml = br#"<w:p>
+            <w:r>
+                <w:drawing>
+                    <wp:anchor behindDoc="0">
+                        <wp:simplePos x="0" y="0"/>
+                        <wp:extent cx="3000000" cy="1500000"/>
+                        <wp:docPr id="4" name="Logo" descr="Tom &amp; Jerry" title="Cartoon"/>
+                        <pic:blipFill><a:blip r:embed="rId9"/></pic:blipFill>
+                    </wp:anchor>
+                </w:drawing>
+            </w:r>
+        </w:p>"#;
+
+        let images = parse_inline_images(xml).unwrap();
+        assert_eq!(images.len(), 1);
+        assert_eq!(images[0].r_embed(), "rId9");
+        assert_eq!(images[0].width_emu(), 3000000);
+        assert_eq!(images[0].description(), "Tom & Jerry");
+        assert_eq!(images[0].title(), "Cartoon");
+    }
 }
@@ -59,10 +59,12 @@ pub mod enums;
 pub mod field;
 pub mod footnote;
 pub mod format;
+pub mod fragment;
 pub mod header_footer;
 pub mod hyperlink;
 pub mod image;
 pub mod numbering;
+pub mod ole_object;
 pub mod package;
 pub mod paragraph;
 pub mod parts;
@@ -73,31 +75,35 @@ pub mod statistics;
 pub mod styles;
 pub mod table;
 pub mod template;
+pub mod text_box;
 pub mod theme;
 pub mod variables;
 pub mod writer;
 
 pub use bookmark::Bookmark;
 pub use comment::Comment;
-pub use content_control::ContentControl;
+pub use content_control::{ContentControl, DataBinding};
 pub use custom_xml::CustomXmlPart;
 pub use document::Document;
 pub use drawing::{DrawingObject, ShapeType};
 pub use enums::{WdHeaderFooter, WdOrientation, WdSectionStart, WdStyleType};
 pub use field::Field;
 pub use footnote::{Note, NoteType};
+pub use fragment::DocumentFragment;
 pub use header_footer::HeaderFooter;
 pub use hyperlink::Hyperlink;
 pub use image::InlineImage;
-pub use numbering::{AbstractNum, Num, Numbering};
+pub use numbering::{AbstractNum, Num, Numbering, NumberingLevel};
+pub use ole_object::OleObject;
 pub use package::Package;
 pub use paragraph::{Paragraph, Run, RunProperties};
 pub use revision::{Revision, RevisionType};
 pub use section::{Emu, Margins, PageSize, Section, Sections};
 pub use settings::{DocumentSettings, ProtectionType};
 pub use statistics::DocumentStatistics;
-pub use styles::{Style, Styles};
-pub use table::{Cell, Row, Table, VMergeState};
+pub use styles::{CharacterFormat, Style, Styles};
+pub use table::{Cell, Row, Table, TableCellProperties, VMergeState};
+pub use text_box::TextBox;
 pub use theme::Theme;
 pub use variables::DocumentVariables;
 // Re-export shared formatting types
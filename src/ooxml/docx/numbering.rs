@@ -2,10 +2,11 @@
 ///
 /// This module provides types and methods for accessing numbering (lists) in Word documents.
 /// Numbering defines how lists and outline numbering are formatted.
+use crate::document::{ListLevelFormat, NumberStyle};
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::part::Part;
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 
 /// Numbering definitions in a Word document.
 ///
@@ -39,6 +40,21 @@ pub struct AbstractNum {
     id: u32,
     /// Numbering type (e.g., "hybridMultilevel", "arabicPeriod")
     num_type: Option<String>,
+    /// Level definitions (`w:lvl`)
+    levels: Vec<NumberingLevel>,
+}
+
+/// A level of an abstract numbering definition (`w:lvl`).
+#[derive(Debug, Clone, Default)]
+pub struct NumberingLevel {
+    /// Level index (0-8)
+    level: u8,
+    /// Value of the first item
+    start: Option<u32>,
+    /// Number format (e.g., "decimal", "lowerLetter", "bullet")
+    format: Option<String>,
+    /// Label template (e.g., "%1.", "%1.%2)")
+    text: Option<String>,
 }
 
 /// A numbering instance (concrete use of an abstract numbering).
@@ -48,6 +64,8 @@ pub struct Num {
     id: u32,
     /// Reference to abstract numbering ID
     abstract_num_id: u32,
+    /// Start values overridden by this instance, as (level, start) pairs
+    start_overrides: Vec<(u8, u32)>,
 }
 
 impl Numbering {
@@ -103,7 +121,7 @@ impl Numbering {
     ///
     /// A Numbering object
     pub(crate) fn extract_from_part(part: &dyn Part) -> Result<Self> {
-        let xml_bytes = part.blob();
+        let xml_bytes = part.try_blob()?;
         let mut reader = Reader::from_reader(xml_bytes);
         reader.config_mut().trim_text(true);
 
@@ -113,8 +131,12 @@ impl Numbering {
         let mut in_num = false;
         let mut current_abstract_id: Option<u32> = None;
         let mut current_abstract_type: Option<String> = None;
+        let mut current_levels: Vec<NumberingLevel> = Vec::new();
+        let mut current_level: Option<NumberingLevel> = None;
         let mut current_num_id: Option<u32> = None;
         let mut current_abstract_num_id: Option<u32> = None;
+        let mut current_start_overrides: Vec<(u8, u32)> = Vec::new();
+        let mut current_override_level: Option<u8> = None;
 
         loop {
             match reader.read_event() {
@@ -124,6 +146,7 @@ impl Numbering {
                             in_abstract_num = true;
                             current_abstract_id = None;
                             current_abstract_type = None;
+                            current_levels.clear();
 
                             for attr in e.attributes().flatten() {
                                 if attr.key.local_name().as_ref() == b"abstractNumId" {
@@ -143,10 +166,32 @@ impl Numbering {
                                 }
                             }
                         },
+                        b"lvl" if in_abstract_num => {
+                            current_level = Some(NumberingLevel {
+                                level: Self::attr_u32(&e, b"ilvl").unwrap_or(0).min(8) as u8,
+                                ..Default::default()
+                            });
+                        },
+                        b"start" => {
+                            if let Some(level) = current_level.as_mut() {
+                                level.start = Self::attr_u32(&e, b"val");
+                            }
+                        },
+                        b"numFmt" => {
+                            if let Some(level) = current_level.as_mut() {
+                                level.format = Self::attr_string(&e, b"val");
+                            }
+                        },
+                        b"lvlText" => {
+                            if let Some(level) = current_level.as_mut() {
+                                level.text = Self::attr_string(&e, b"val");
+                            }
+                        },
                         b"num" if !in_abstract_num => {
                             in_num = true;
                             current_num_id = None;
                             current_abstract_num_id = None;
+                            current_start_overrides.clear();
 
                             for attr in e.attributes().flatten() {
                                 if attr.key.local_name().as_ref() == b"numId" {
@@ -168,15 +213,32 @@ impl Numbering {
                                 }
                             }
                         },
+                        b"lvlOverride" if in_num => {
+                            current_override_level =
+                                Self::attr_u32(&e, b"ilvl").map(|level| level.min(8) as u8);
+                        },
+                        b"startOverride" if in_num => {
+                            if let (Some(level), Some(start)) =
+                                (current_override_level, Self::attr_u32(&e, b"val"))
+                            {
+                                current_start_overrides.push((level, start));
+                            }
+                        },
                         _ => {},
                     }
                 },
                 Ok(Event::End(e)) => match e.local_name().as_ref() {
+                    b"lvl" => {
+                        if let Some(level) = current_level.take() {
+                            current_levels.push(level);
+                        }
+                    },
                     b"abstractNum" => {
                         if let Some(id) = current_abstract_id {
                             abstract_nums.push(AbstractNum {
                                 id,
                                 num_type: current_abstract_type.clone(),
+                                levels: std::mem::take(&mut current_levels),
                             });
                         }
                         in_abstract_num = false;
@@ -188,6 +250,7 @@ impl Numbering {
                             nums.push(Num {
                                 id,
                                 abstract_num_id: abstract_id,
+                                start_overrides: std::mem::take(&mut current_start_overrides),
                             });
                         }
                         in_num = false;
@@ -205,6 +268,57 @@ impl Numbering {
             nums,
         })
     }
+
+    /// Resolve the level formats of a numbering instance.
+    ///
+    /// Returns a key identifying the list whose counters the instance uses,
+    /// and the formats of its levels. Instances of the same abstract
+    /// numbering continue each other unless they override start values.
+    pub(crate) fn level_formats(&self, num_id: u32) -> Option<((u32, u32), Vec<ListLevelFormat>)> {
+        let num = self.get_num(num_id)?;
+        let abstract_num = self.get_abstract_num(num.abstract_num_id)?;
+
+        let mut formats = vec![
+            ListLevelFormat {
+                start: 1,
+                style: NumberStyle::None,
+                text: String::new(),
+            };
+            abstract_num
+                .levels
+                .iter()
+                .map(|l| l.level as usize + 1)
+                .max()?
+        ];
+        for level in &abstract_num.levels {
+            formats[level.level as usize] = ListLevelFormat {
+                start: num.start_override(level.level).unwrap_or(level.start()),
+                style: level.number_style(),
+                text: level.text().unwrap_or_default().to_string(),
+            };
+        }
+
+        let key = if num.start_overrides.is_empty() {
+            (abstract_num.id, 0)
+        } else {
+            (abstract_num.id, num.id)
+        };
+        Some((key, formats))
+    }
+
+    fn attr_string(e: &BytesStart<'_>, name: &[u8]) -> Option<String> {
+        e.attributes()
+            .flatten()
+            .find(|attr| attr.key.local_name().as_ref() == name)
+            .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+    }
+
+    fn attr_u32(e: &BytesStart<'_>, name: &[u8]) -> Option<u32> {
+        e.attributes()
+            .flatten()
+            .find(|attr| attr.key.local_name().as_ref() == name)
+            .and_then(|attr| atoi_simd::parse::<u32, false, false>(&attr.value).ok())
+    }
 }
 
 impl Default for Numbering {
@@ -225,6 +339,52 @@ impl AbstractNum {
     pub fn num_type(&self) -> Option<&str> {
         self.num_type.as_deref()
     }
+
+    /// Get the level definitions.
+    #[inline]
+    pub fn levels(&self) -> &[NumberingLevel] {
+        &self.levels
+    }
+}
+
+impl NumberingLevel {
+    /// Get the level index (0-8).
+    #[inline]
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Get the value of the first item (defaults to 0, as in Word).
+    #[inline]
+    pub fn start(&self) -> u32 {
+        self.start.unwrap_or(0)
+    }
+
+    /// Get the number format (`w:numFmt`), e.g. "decimal" or "bullet".
+    #[inline]
+    pub fn format(&self) -> Option<&str> {
+        self.format.as_deref()
+    }
+
+    /// Get the label template (`w:lvlText`), e.g. "%1.".
+    #[inline]
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    /// Map the number format to the styles used to compute labels.
+    fn number_style(&self) -> NumberStyle {
+        match self.format.as_deref() {
+            Some("bullet") => NumberStyle::Bullet,
+            Some("none") => NumberStyle::None,
+            Some("lowerLetter") => NumberStyle::LowerLetter,
+            Some("upperLetter") => NumberStyle::UpperLetter,
+            Some("lowerRoman") => NumberStyle::LowerRoman,
+            Some("upperRoman") => NumberStyle::UpperRoman,
+            // Word falls back to decimal for formats it cannot render
+            _ => NumberStyle::Decimal,
+        }
+    }
 }
 
 impl Num {
@@ -239,6 +399,14 @@ impl Num {
     pub fn abstract_num_id(&self) -> u32 {
         self.abstract_num_id
     }
+
+    /// Get the start value this instance sets for a level (`w:startOverride`).
+    pub fn start_override(&self, level: u8) -> Option<u32> {
+        self.start_overrides
+            .iter()
+            .find(|(l, _)| *l == level)
+            .map(|(_, start)| *start)
+    }
 }
 
 #[cfg(test)]
@@ -274,10 +442,12 @@ mod tests {
         numbering.abstract_nums.push(AbstractNum {
             id: 1,
             num_type: Some("hybridMultilevel".to_string()),
+            levels: Vec::new(),
         });
         numbering.abstract_nums.push(AbstractNum {
             id: 2,
             num_type: Some("arabicPeriod".to_string()),
+            levels: Vec::new(),
         });
 
         assert_eq!(numbering.abstract_num_count(), 2);
@@ -292,10 +462,12 @@ mod tests {
         numbering.nums.push(Num {
             id: 10,
             abstract_num_id: 1,
+            start_overrides: Vec::new(),
         });
         numbering.nums.push(Num {
             id: 11,
             abstract_num_id: 2,
+            start_overrides: Vec::new(),
         });
 
         assert_eq!(numbering.num_count(), 2);
@@ -309,6 +481,7 @@ mod tests {
         let abstract_num = AbstractNum {
             id: 5,
             num_type: Some("bullet".to_string()),
+            levels: Vec::new(),
         };
 
         assert_eq!(abstract_num.id(), 5);
@@ -320,6 +493,7 @@ mod tests {
         let abstract_num = AbstractNum {
             id: 3,
             num_type: None,
+            levels: Vec::new(),
         };
 
         assert_eq!(abstract_num.id(), 3);
@@ -331,6 +505,7 @@ mod tests {
         let abstract_num = AbstractNum {
             id: 7,
             num_type: Some("roman".to_string()),
+            levels: Vec::new(),
         };
         let cloned = abstract_num.clone();
 
@@ -343,6 +518,7 @@ mod tests {
         let abstract_num = AbstractNum {
             id: 1,
             num_type: Some("test".to_string()),
+            levels: Vec::new(),
         };
         let debug_str = format!("{:?}", abstract_num);
         assert!(debug_str.contains("AbstractNum"));
@@ -354,6 +530,7 @@ mod tests {
         let num = Num {
             id: 15,
             abstract_num_id: 3,
+            start_overrides: Vec::new(),
         };
 
         assert_eq!(num.id(), 15);
@@ -365,6 +542,7 @@ mod tests {
         let num = Num {
             id: 20,
             abstract_num_id: 5,
+            start_overrides: Vec::new(),
         };
         let cloned = num.clone();
 
@@ -377,6 +555,7 @@ mod tests {
         let num = Num {
             id: 1,
             abstract_num_id: 2,
+            start_overrides: Vec::new(),
         };
         let debug_str = format!("{:?}", num);
         assert!(debug_str.contains("Num"));
@@ -390,10 +569,12 @@ mod tests {
         numbering.abstract_nums.push(AbstractNum {
             id: 1,
             num_type: Some("type1".to_string()),
+            levels: Vec::new(),
         });
         numbering.nums.push(Num {
             id: 10,
             abstract_num_id: 1,
+            start_overrides: Vec::new(),
         });
 
         let cloned = numbering.clone();
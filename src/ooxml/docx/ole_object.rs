@@ -0,0 +1,208 @@
+/// Embedded OLE object support for Word documents.
+///
+/// This module provides types for reading objects embedded through OLE, such
+/// as Excel ranges, Visio drawings or packaged files. Each object is a
+/// `<w:object>` holding an `<o:OLEObject>` that points to the object part
+/// (`embeddings/oleObject1.bin`, or the embedded file itself for Office Open
+/// XML packages) and a VML shape whose `<v:imagedata>` is the preview image.
+use crate::ooxml::error::{OoxmlError, Result};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+/// An OLE object embedded in a Word document.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::ooxml::docx::Package;
+///
+/// let pkg = Package::open("document.docx")?;
+/// let doc = pkg.document()?;
+///
+/// for object in doc.ole_objects()? {
+///     println!(
+///         "{} ({} bytes)",
+///         object.prog_id().unwrap_or("unknown"),
+///         object.data().len()
+///     );
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct OleObject {
+    /// Programmatic identifier of the object's class, e.g. `Excel.Sheet.12`
+    prog_id: Option<String>,
+    /// Name of the object part
+    partname: String,
+    /// Content of the object part
+    data: Vec<u8>,
+    /// Name of the preview image part
+    preview_partname: Option<String>,
+    /// Content of the preview image part
+    preview: Option<Vec<u8>>,
+}
+
+impl OleObject {
+    /// Create a new OleObject.
+    pub fn new(
+        prog_id: Option<String>,
+        partname: String,
+        data: Vec<u8>,
+        preview_partname: Option<String>,
+        preview: Option<Vec<u8>>,
+    ) -> Self {
+        Self {
+            prog_id,
+            partname,
+            data,
+            preview_partname,
+            preview,
+        }
+    }
+
+    /// Get the programmatic identifier of the object's class.
+    #[inline]
+    pub fn prog_id(&self) -> Option<&str> {
+        self.prog_id.as_deref()
+    }
+
+    /// Get the name of the object part, e.g. `/word/embeddings/oleObject1.bin`.
+    #[inline]
+    pub fn partname(&self) -> &str {
+        &self.partname
+    }
+
+    /// Get the content of the object part.
+    ///
+    /// This is a compound file for OLE objects, or the embedded file itself
+    /// for Office Open XML packages such as `.xlsx` workbooks.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get the name of the preview image part, e.g. `/word/media/image1.wmf`.
+    #[inline]
+    pub fn preview_partname(&self) -> Option<&str> {
+        self.preview_partname.as_deref()
+    }
+
+    /// Get the content of the preview image part (usually EMF or WMF).
+    #[inline]
+    pub fn preview(&self) -> Option<&[u8]> {
+        self.preview.as_deref()
+    }
+}
+
+/// Relationship IDs of an OLE object found in the document XML.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub(crate) struct OleObjectReference {
+    /// Programmatic identifier of the object's class
+    pub prog_id: Option<String>,
+    /// Relationship ID of the object part
+    pub r_id: String,
+    /// Relationship ID of the preview image
+    pub preview_r_id: Option<String>,
+}
+
+/// Extract the embedded OLE objects referenced by document XML bytes.
+///
+/// Linked objects (`Type="Link"`) point to external files and are skipped.
+pub(crate) fn extract_references(doc_xml: &[u8]) -> Result<Vec<OleObjectReference>> {
+    let mut reader = Reader::from_reader(doc_xml);
+    reader.config_mut().trim_text(true);
+
+    let mut references = Vec::new();
+    let mut current: Option<OleObjectReference> = None;
+    let mut linked = false;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"object" => {
+                current = Some(OleObjectReference::default());
+                linked = false;
+            },
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                let Some(object) = current.as_mut() else {
+                    continue;
+                };
+                match e.local_name().as_ref() {
+                    // o:OLEObject, or w:objectEmbed/w:objectLink of newer files
+                    b"OLEObject" | b"objectEmbed" | b"objectLink" => {
+                        linked = e.local_name().as_ref() == b"objectLink";
+                        for attr in e.attributes().flatten() {
+                            let value = String::from_utf8_lossy(&attr.value).into_owned();
+                            match attr.key.local_name().as_ref() {
+                                b"ProgID" | b"progId" => object.prog_id = Some(value),
+                                b"Type" => linked = value == "Link",
+                                b"id" if attr.key.prefix().is_some() => object.r_id = value,
+                                _ => {},
+                            }
+                        }
+                    },
+                    b"imagedata" => {
+                        if let Some(r_id) = relationship_attr(&e, b"id") {
+                            object.preview_r_id = Some(r_id);
+                        }
+                    },
+                    b"blip" => {
+                        if let Some(r_id) = relationship_attr(&e, b"embed") {
+                            object.preview_r_id = Some(r_id);
+                        }
+                    },
+                    _ => {},
+                }
+            },
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"object" => {
+                if let Some(object) = current.take()
+                    && !object.r_id.is_empty()
+                    && !linked
+                {
+                    references.push(object);
+                }
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+            _ => {},
+        }
+    }
+
+    Ok(references)
+}
+
+/// Get a namespaced relationship attribute (`r:id`, `r:embed`) of an element
+fn relationship_attr(e: &BytesStart, local_name: &[u8]) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.prefix().is_some() && attr.key.local_name().as_ref() == local_name)
+        .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_references() {
+        let xml = br##"<w:document xmlns:w="w" xmlns:v="v" xmlns:o="o" xmlns:r="r"><w:body>
+            <w:p><w:r><w:object w:dxaOrig="1440" w:dyaOrig="960">
+                <v:shape id="_x0000_i1025" type="#_x0000_t75"><v:imagedata r:id="rId4" o:title=""/></v:shape>
+                <o:OLEObject Type="Embed" ProgID="Package" ShapeID="_x0000_i1025" DrawAspect="Icon" ObjectID="_1" r:id="rId5"/>
+            </w:object></w:r></w:p>
+            <w:p><w:r><w:object>
+                <v:shape><v:imagedata r:id="rId6"/></v:shape>
+                <o:OLEObject Type="Link" ProgID="Excel.Sheet.12" r:id="rId7"/>
+            </w:object></w:r></w:p>
+        </w:body></w:document>"##;
+
+        let references = extract_references(xml).unwrap();
+        assert_eq!(
+            references,
+            vec![OleObjectReference {
+                prog_id: Some("Package".to_string()),
+                r_id: "rId5".to_string(),
+                preview_r_id: Some("rId4".to_string()),
+            }]
+        );
+    }
+}
@@ -1,6 +1,9 @@
+use crate::common::Metadata;
 use crate::ooxml::common::DocumentProperties;
+use crate::ooxml::common::properties::write_property_part;
 use crate::ooxml::custom_properties::CustomProperties;
 use crate::ooxml::docx::document::Document;
+use crate::ooxml::docx::enums::WdHeaderFooter;
 use crate::ooxml::docx::parts::DocumentPart;
 use crate::ooxml::docx::writer::MutableDocument;
 /// Package implementation for Word documents.
@@ -103,7 +106,7 @@ impl EmbedFonts for Package {
             .map_err(|e| OoxmlError::Other(format!("Invalid settings URI: {}", e)))?;
 
         if let Ok(settings_part) = self.opc.get_part_mut(&settings_uri) {
-            let xml_content = std::str::from_utf8(settings_part.blob())
+            let xml_content = std::str::from_utf8(settings_part.try_blob()?)
                 .map_err(|e| OoxmlError::Other(format!("Invalid settings.xml: {}", e)))?;
 
             // Check if embedTrueTypeFonts already exists
@@ -120,7 +123,7 @@ impl EmbedFonts for Package {
 
         // Update fontTable.xml content with embedded font references
         if let Ok(font_table_part) = self.opc.get_part_mut(&font_table_uri) {
-            let xml_content = std::str::from_utf8(font_table_part.blob())
+            let xml_content = std::str::from_utf8(font_table_part.try_blob()?)
                 .map_err(|e| OoxmlError::Other(format!("Invalid fontTable.xml: {}", e)))?;
 
             let mut updated_xml = xml_content.to_string();
@@ -395,9 +398,14 @@ impl Package {
             .map_err(|e| OoxmlError::PartNotFound(format!("main document part: {}", e)))?;
 
         let content_type = main_part.content_type();
-        if content_type != ct::WML_DOCUMENT_MAIN {
+        // Support both regular and macro-enabled documents
+        if content_type != ct::WML_DOCUMENT_MAIN && content_type != ct::WML_DOCUMENT_MACRO_MAIN {
             return Err(OoxmlError::InvalidContentType {
-                expected: ct::WML_DOCUMENT_MAIN.to_string(),
+                expected: format!(
+                    "{} or {}",
+                    ct::WML_DOCUMENT_MAIN,
+                    ct::WML_DOCUMENT_MACRO_MAIN
+                ),
                 got: content_type.to_string(),
             });
         }
@@ -406,10 +414,12 @@ impl Package {
         let custom_properties = crate::ooxml::custom_properties::extract_custom_properties(&opc)
             .unwrap_or_else(|_| CustomProperties::new());
 
+        let properties = DocumentProperties::from_package(&opc);
+
         Ok(Self {
             opc,
             mutable_doc: None,
-            properties: DocumentProperties::new(),
+            properties,
             custom_properties,
         })
     }
@@ -449,9 +459,14 @@ impl Package {
             .map_err(|e| OoxmlError::PartNotFound(format!("main document part: {}", e)))?;
 
         let content_type = main_part.content_type();
-        if content_type != ct::WML_DOCUMENT_MAIN {
+        // Support both regular and macro-enabled documents
+        if content_type != ct::WML_DOCUMENT_MAIN && content_type != ct::WML_DOCUMENT_MACRO_MAIN {
             return Err(OoxmlError::InvalidContentType {
-                expected: ct::WML_DOCUMENT_MAIN.to_string(),
+                expected: format!(
+                    "{} or {}",
+                    ct::WML_DOCUMENT_MAIN,
+                    ct::WML_DOCUMENT_MACRO_MAIN
+                ),
                 got: content_type.to_string(),
             });
         }
@@ -460,10 +475,12 @@ impl Package {
         let custom_properties = crate::ooxml::custom_properties::extract_custom_properties(&opc)
             .unwrap_or_else(|_| CustomProperties::new());
 
+        let properties = DocumentProperties::from_package(&opc);
+
         Ok(Self {
             opc,
             mutable_doc: None,
-            properties: DocumentProperties::new(),
+            properties,
             custom_properties,
         })
     }
@@ -494,9 +511,14 @@ impl Package {
             .map_err(|e| OoxmlError::PartNotFound(format!("main document part: {}", e)))?;
 
         let content_type = main_part.content_type();
-        if content_type != ct::WML_DOCUMENT_MAIN {
+        // Support both regular and macro-enabled documents
+        if content_type != ct::WML_DOCUMENT_MAIN && content_type != ct::WML_DOCUMENT_MACRO_MAIN {
             return Err(OoxmlError::InvalidContentType {
-                expected: ct::WML_DOCUMENT_MAIN.to_string(),
+                expected: format!(
+                    "{} or {}",
+                    ct::WML_DOCUMENT_MAIN,
+                    ct::WML_DOCUMENT_MACRO_MAIN
+                ),
                 got: content_type.to_string(),
             });
         }
@@ -505,10 +527,12 @@ impl Package {
         let custom_properties = crate::ooxml::custom_properties::extract_custom_properties(&opc)
             .unwrap_or_else(|_| CustomProperties::new());
 
+        let properties = DocumentProperties::from_package(&opc);
+
         Ok(Self {
             opc,
             mutable_doc: None,
-            properties: DocumentProperties::new(),
+            properties,
             custom_properties,
         })
     }
@@ -601,7 +625,7 @@ impl Package {
 
             // Try to get existing document content
             if let Ok(part) = self.opc.get_part(&doc_uri) {
-                let xml = std::str::from_utf8(part.blob())
+                let xml = std::str::from_utf8(part.try_blob()?)
                     .map_err(|e| OoxmlError::InvalidFormat(format!("Invalid UTF-8: {}", e)))?;
                 self.mutable_doc = Some(MutableDocument::from_xml(xml)?);
             } else {
@@ -673,8 +697,6 @@ impl Package {
                 // Step 1: Collect all content that needs relationships
                 let hyperlink_urls = mutable_doc.collect_hyperlink_urls();
                 let images = mutable_doc.collect_images();
-                let has_header = mutable_doc.has_header();
-                let has_footer = mutable_doc.has_footer();
 
                 // Step 2: Create a relationship mapper and add relationships
                 let mut rel_mapper = RelationshipMapper::new();
@@ -708,11 +730,13 @@ impl Package {
                                 | "http://schemas.openxmlformats.org/officeDocument/2006/relationships/footnotes"
                                 | "http://schemas.openxmlformats.org/officeDocument/2006/relationships/endnotes"
                         ) {
-                            if rel.is_external() {
-                                temp_part.relate_to_ext(rel.target_ref(), rel.reltype());
-                            } else {
-                                temp_part.relate_to(rel.target_ref(), rel.reltype());
-                            }
+                            // Keep the original ids so references from other parts stay valid
+                            temp_part.rels_mut().add_relationship(
+                                rel.reltype().to_string(),
+                                rel.target_ref().to_string(),
+                                rel.r_id().to_string(),
+                                rel.is_external(),
+                            );
                         }
                     }
                 }
@@ -744,38 +768,46 @@ impl Package {
                     rel_mapper.add_image(i, rid);
                 }
 
-                // Add header/footer parts and relationships
-                // Note: If watermark exists, headers will be handled by update_watermark_headers
-                // which merges user content with watermark
-                if has_header
-                    && !mutable_doc.has_watermark()
-                    && let Some(header_xml) = mutable_doc.generate_header_xml()?
-                {
-                    let header_uri = PackURI::new("/word/header1.xml")
-                        .map_err(|e| OoxmlError::InvalidUri(format!("header URI: {}", e)))?;
-                    let header_part = BlobPart::new(
-                        header_uri,
-                        ct::WML_HEADER.to_string(),
-                        header_xml.into_bytes(),
-                    );
-                    self.opc.add_part(Box::new(header_part));
-                    // Use relative path for relationship (relative to document.xml location)
-                    let rid = temp_part.relate_to("header1.xml", rt::HEADER);
-                    rel_mapper.set_header_id(rid);
-                }
+                // Add header/footer parts and relationships, one part per kind
+                // Note: If watermark exists, headers are created together with the
+                // watermark further down
+                let kinds = [
+                    WdHeaderFooter::Primary,
+                    WdHeaderFooter::FirstPage,
+                    WdHeaderFooter::EvenPage,
+                ];
+                for (idx, kind) in kinds.into_iter().enumerate() {
+                    if !mutable_doc.has_watermark()
+                        && let Some(header_xml) = mutable_doc.generate_header_xml_of(kind)?
+                    {
+                        let header_filename = format!("header{}.xml", idx + 1);
+                        let header_uri = PackURI::new(format!("/word/{}", header_filename))
+                            .map_err(|e| OoxmlError::InvalidUri(format!("header URI: {}", e)))?;
+                        let header_part = BlobPart::new(
+                            header_uri,
+                            ct::WML_HEADER.to_string(),
+                            header_xml.into_bytes(),
+                        );
+                        self.opc.add_part(Box::new(header_part));
+                        // Use relative path for relationship (relative to document.xml location)
+                        let rid = temp_part.relate_to(&header_filename, rt::HEADER);
+                        rel_mapper.set_header_reference(kind, rid);
+                    }
 
-                if has_footer && let Some(footer_xml) = mutable_doc.generate_footer_xml()? {
-                    let footer_uri = PackURI::new("/word/footer1.xml")
-                        .map_err(|e| OoxmlError::InvalidUri(format!("footer URI: {}", e)))?;
-                    let footer_part = BlobPart::new(
-                        footer_uri,
-                        ct::WML_FOOTER.to_string(),
-                        footer_xml.into_bytes(),
-                    );
-                    self.opc.add_part(Box::new(footer_part));
-                    // Use relative path for relationship (relative to document.xml location)
-                    let rid = temp_part.relate_to("footer1.xml", rt::FOOTER);
-                    rel_mapper.set_footer_id(rid);
+                    if let Some(footer_xml) = mutable_doc.generate_footer_xml_of(kind)? {
+                        let footer_filename = format!("footer{}.xml", idx + 1);
+                        let footer_uri = PackURI::new(format!("/word/{}", footer_filename))
+                            .map_err(|e| OoxmlError::InvalidUri(format!("footer URI: {}", e)))?;
+                        let footer_part = BlobPart::new(
+                            footer_uri,
+                            ct::WML_FOOTER.to_string(),
+                            footer_xml.into_bytes(),
+                        );
+                        self.opc.add_part(Box::new(footer_part));
+                        // Use relative path for relationship (relative to document.xml location)
+                        let rid = temp_part.relate_to(&footer_filename, rt::FOOTER);
+                        rel_mapper.set_footer_reference(kind, rid);
+                    }
                 }
 
                 // Add footnotes parts and relationships BEFORE document XML generation
@@ -809,57 +841,40 @@ impl Package {
                 // Handle watermark headers before generating document XML
                 // This ensures header relationships are properly set up
                 if mutable_doc.has_watermark() {
-                    // Generate user header content if exists (will be merged with watermark)
-                    let user_header_content = if mutable_doc.has_header() {
-                        mutable_doc.generate_header_xml()?
-                    } else {
-                        None
-                    };
-
-                    // Create three headers (default, first, even) with watermark
-                    let header_types = [
-                        ("/word/header1.xml", "header1.xml"),
-                        ("/word/header2.xml", "header2.xml"),
-                        ("/word/header3.xml", "header3.xml"),
-                    ];
-
-                    for (idx, (header_uri_path, header_filename)) in header_types.iter().enumerate()
-                    {
+                    // Create three headers (default, first, even) with watermark; Word
+                    // only uses the first and even ones when the section asks for them
+                    for (idx, kind) in kinds.into_iter().enumerate() {
                         if let Some(wm) = mutable_doc.watermark.as_ref() {
                             let watermark_xml = wm.to_header_xml((idx + 1) as u32)?;
 
-                            // Merge user header content with watermark for the default header
-                            let header_xml = if idx == 0
-                                && let Some(ref user_content) = user_header_content
-                            {
+                            // Merge user header content of the same kind with the watermark
+                            let user_paragraphs = match mutable_doc.generate_header_xml_of(kind)? {
                                 // Extract user paragraphs from the <w:hdr>...</w:hdr> wrapper
-                                let user_paragraphs = if let Some(start) = user_content.find("<w:p")
-                                {
-                                    if let Some(end) = user_content.rfind("</w:hdr>") {
-                                        &user_content[start..end]
-                                    } else {
-                                        ""
+                                Some(user_content) => {
+                                    match (
+                                        user_content.find("<w:p"),
+                                        user_content.rfind("</w:hdr>"),
+                                    ) {
+                                        (Some(start), Some(end)) => {
+                                            user_content[start..end].to_string()
+                                        },
+                                        _ => String::new(),
                                     }
-                                } else {
-                                    ""
-                                };
-
-                                // Combine watermark and user content
-                                format!(
-                                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:hdr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office">{}{}</w:hdr>"#,
-                                    watermark_xml, user_paragraphs
-                                )
-                            } else {
-                                // Just watermark for first and even headers
-                                format!(
-                                    r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:hdr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office">{}</w:hdr>"#,
-                                    watermark_xml
-                                )
+                                },
+                                None => String::new(),
                             };
 
-                            let header_uri = PackURI::new(*header_uri_path).map_err(|e| {
-                                OoxmlError::InvalidUri(format!("header URI: {}", e))
-                            })?;
+                            // Combine watermark and user content
+                            let header_xml = format!(
+                                r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:hdr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:v="urn:schemas-microsoft-com:vml" xmlns:o="urn:schemas-microsoft-com:office:office">{}{}</w:hdr>"#,
+                                watermark_xml, user_paragraphs
+                            );
+
+                            let header_filename = format!("header{}.xml", idx + 1);
+                            let header_uri = PackURI::new(format!("/word/{}", header_filename))
+                                .map_err(|e| {
+                                    OoxmlError::InvalidUri(format!("header URI: {}", e))
+                                })?;
 
                             let header_part = BlobPart::new(
                                 header_uri,
@@ -869,14 +884,8 @@ impl Package {
 
                             self.opc.add_part(Box::new(header_part));
 
-                            // Add relationship for the default header
-                            if idx == 0 {
-                                let rid = temp_part.relate_to(header_filename, rt::HEADER);
-                                rel_mapper.set_header_id(rid);
-                            } else {
-                                // Other headers are added but not set in rel_mapper (they're referenced in sectPr)
-                                temp_part.relate_to(header_filename, rt::HEADER);
-                            }
+                            let rid = temp_part.relate_to(&header_filename, rt::HEADER);
+                            rel_mapper.set_header_reference(kind, rid);
                         }
                     }
                 }
@@ -909,8 +918,59 @@ impl Package {
             self.mutable_doc = Some(mutable_doc);
         }
 
-        // Update core properties
+        // Add named styles, before fragments are matched against the styles
+        let styles = self
+            .mutable_doc
+            .as_ref()
+            .map(|doc| doc.styles().to_vec())
+            .unwrap_or_default();
+        if !styles.is_empty() {
+            let doc_uri = PackURI::new("/word/document.xml")
+                .map_err(|e| OoxmlError::InvalidUri(format!("document URI: {}", e)))?;
+            crate::ooxml::docx::writer::style::apply_styles(&mut self.opc, &doc_uri, &styles)?;
+        }
+
+        // Insert fragments at bookmarks, after any regenerated document XML
+        let insertions = self
+            .mutable_doc
+            .as_ref()
+            .map(|doc| doc.fragment_insertions().to_vec())
+            .unwrap_or_default();
+        if !insertions.is_empty() {
+            let doc_uri = PackURI::new("/word/document.xml")
+                .map_err(|e| OoxmlError::InvalidUri(format!("document URI: {}", e)))?;
+            for insertion in &insertions {
+                crate::ooxml::docx::writer::fragment::insert_fragment(
+                    &mut self.opc,
+                    &doc_uri,
+                    insertion,
+                )?;
+            }
+        }
+
+        // Fill in content controls
+        let values = self
+            .mutable_doc
+            .as_ref()
+            .map(|doc| doc.content_control_values().to_vec())
+            .unwrap_or_default();
+        if !values.is_empty() {
+            self.fill_content_controls(&values)?;
+        }
+
+        // Update or flatten fields
+        let operations = self
+            .mutable_doc
+            .as_ref()
+            .map(|doc| doc.field_operations().to_vec())
+            .unwrap_or_default();
+        if !operations.is_empty() {
+            self.apply_field_operations(operations)?;
+        }
+
+        // Update core and extended properties
         self.update_core_properties()?;
+        self.update_app_properties()?;
 
         // Update custom properties
         self.update_custom_properties()?;
@@ -961,6 +1021,32 @@ impl Package {
         &mut self.properties
     }
 
+    /// Replace the document properties and custom properties with `metadata`.
+    ///
+    /// Core fields go to docProps/core.xml, application/company/manager/template
+    /// to docProps/app.xml and the custom property map to docProps/custom.xml
+    /// when the package is saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::common::Metadata;
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let mut pkg = Package::new()?;
+    /// pkg.set_metadata(&Metadata {
+    ///     title: Some("Quarterly Report".to_string()),
+    ///     author: Some("Jane Doe".to_string()),
+    ///     ..Default::default()
+    /// });
+    /// pkg.save("report.docx")?;
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn set_metadata(&mut self, metadata: &Metadata) {
+        self.properties = DocumentProperties::from(metadata);
+        self.custom_properties = CustomProperties::from(&metadata.custom_properties);
+    }
+
     /// Get a reference to the custom document properties.
     ///
     /// Custom properties allow you to attach arbitrary typed metadata to documents.
@@ -1006,56 +1092,53 @@ impl Package {
 
     /// Update the core.xml properties part.
     fn update_core_properties(&mut self) -> Result<()> {
-        use crate::ooxml::opc::part::BlobPart;
-
-        let core_uri = PackURI::new("/docProps/core.xml")
-            .map_err(|e| OoxmlError::InvalidUri(format!("core.xml URI: {}", e)))?;
+        use crate::ooxml::opc::constants::relationship_type as rt;
 
-        // Generate XML from properties
+        self.properties.stamp();
         let xml = self.properties.to_xml();
+        write_property_part(
+            &mut self.opc,
+            rt::CORE_PROPERTIES,
+            "/docProps/core.xml",
+            ct::OPC_CORE_PROPERTIES,
+            |_| Ok(xml),
+        )
+    }
 
-        // Create or update the core properties part
-        let core_part = BlobPart::new(
-            core_uri,
-            ct::OPC_CORE_PROPERTIES.to_string(),
-            xml.into_bytes(),
-        );
-
-        self.opc.add_part(Box::new(core_part));
+    /// Merge the extended properties into the app.xml part.
+    fn update_app_properties(&mut self) -> Result<()> {
+        use crate::ooxml::opc::constants::relationship_type as rt;
 
-        Ok(())
+        let properties = &self.properties;
+        write_property_part(
+            &mut self.opc,
+            rt::EXTENDED_PROPERTIES,
+            "/docProps/app.xml",
+            ct::OFC_EXTENDED_PROPERTIES,
+            |existing| match existing.and_then(|xml| std::str::from_utf8(xml).ok()) {
+                Some(xml) => properties.merge_app_xml(xml),
+                None => properties.to_app_xml(),
+            },
+        )
     }
 
     /// Update the custom.xml properties part.
     fn update_custom_properties(&mut self) -> Result<()> {
         use crate::ooxml::opc::constants::relationship_type as rt;
-        use crate::ooxml::opc::part::BlobPart;
 
         // Only create custom properties part if there are custom properties
         if self.custom_properties.is_empty() {
             return Ok(());
         }
 
-        let custom_uri = PackURI::new("/docProps/custom.xml")
-            .map_err(|e| OoxmlError::InvalidUri(format!("custom.xml URI: {}", e)))?;
-
-        // Generate XML from custom properties
         let xml = self.custom_properties.to_xml()?;
-
-        // Create or update the custom properties part
-        let custom_part = BlobPart::new(
-            custom_uri.clone(),
-            ct::OFC_CUSTOM_PROPERTIES.to_string(),
-            xml.into_bytes(),
-        );
-
-        self.opc.add_part(Box::new(custom_part));
-
-        // Ensure relationship exists
-        self.opc
-            .relate_to("docProps/custom.xml", rt::CUSTOM_PROPERTIES);
-
-        Ok(())
+        write_property_part(
+            &mut self.opc,
+            rt::CUSTOM_PROPERTIES,
+            "/docProps/custom.xml",
+            ct::OFC_CUSTOM_PROPERTIES,
+            |_| Ok(xml),
+        )
     }
 
     /// Update the footnotes.xml part with new content.
@@ -1155,6 +1238,153 @@ impl Package {
         Ok(())
     }
 
+    /// Write content control values into the document part and the custom
+    /// XML parts bound to the filled controls.
+    fn fill_content_controls(
+        &mut self,
+        values: &[(String, crate::ooxml::docx::writer::ContentControlValue)],
+    ) -> Result<()> {
+        use crate::ooxml::docx::writer::form;
+        use crate::ooxml::opc::constants::relationship_type as rt;
+
+        let doc_uri = PackURI::new("/word/document.xml")
+            .map_err(|e| OoxmlError::InvalidUri(format!("document URI: {}", e)))?;
+        let doc_part = self.opc.get_part_mut(&doc_uri)?;
+        let (xml, updates) = form::fill_content_controls(doc_part.try_blob()?, values)?;
+        doc_part.set_blob(xml);
+        if updates.is_empty() {
+            return Ok(());
+        }
+
+        // Custom XML items of the document with their store item IDs
+        let mut items = Vec::new();
+        for rel in self.opc.get_part(&doc_uri)?.rels().iter() {
+            if rel.reltype() != rt::CUSTOM_XML || rel.is_external() {
+                continue;
+            }
+            let Ok(item_uri) = rel.target_partname() else {
+                continue;
+            };
+            let Ok(item) = self.opc.get_part(&item_uri) else {
+                continue;
+            };
+            let mut item_id = None;
+            for props in item
+                .rels()
+                .iter()
+                .filter(|rel| rel.reltype() == rt::CUSTOM_XML_PROPS)
+                .filter_map(|rel| self.opc.get_part(&rel.target_partname().ok()?).ok())
+            {
+                item_id = form::store_item_id(props.try_blob()?);
+                if item_id.is_some() {
+                    break;
+                }
+            }
+            items.push((item_uri, item_id));
+        }
+
+        for update in updates {
+            let store_item_id = update.binding.store_item_id();
+            for (item_uri, item_id) in &items {
+                // Without a store item ID, the first part holding the node is used
+                if let Some(wanted) = store_item_id
+                    && !item_id
+                        .as_deref()
+                        .is_some_and(|id| id.eq_ignore_ascii_case(wanted))
+                {
+                    continue;
+                }
+                let item = self.opc.get_part_mut(item_uri)?;
+                if let Some(xml) =
+                    form::update_bound_node(item.try_blob()?, &update.binding, &update.value)?
+                {
+                    item.set_blob(xml);
+                    break;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Apply field operations to the document part and the header, footer,
+    /// footnote and endnote parts it uses.
+    fn apply_field_operations(
+        &mut self,
+        operations: Vec<crate::ooxml::docx::writer::merge::FieldOperation>,
+    ) -> Result<()> {
+        use crate::ooxml::custom_properties::PropertyValue;
+        use crate::ooxml::docx::writer::merge::{FieldOperation, apply_field_operation};
+        use crate::ooxml::opc::constants::relationship_type as rt;
+
+        // DOCPROPERTY falls back on the properties of the package
+        let props = &self.properties;
+        let mut defaults: Vec<(String, String)> = [
+            ("Title", &props.title),
+            ("Subject", &props.subject),
+            ("Author", &props.creator),
+            ("Keywords", &props.keywords),
+            ("Comments", &props.description),
+            ("LastSavedBy", &props.last_modified_by),
+            ("Category", &props.category),
+            ("Company", &props.company),
+            ("Manager", &props.manager),
+            ("Template", &props.template),
+        ]
+        .into_iter()
+        .filter_map(|(name, value)| Some((name.to_string(), value.clone()?)))
+        .collect();
+        defaults.extend(self.custom_properties.iter().map(|(name, value)| {
+            let value = match value {
+                PropertyValue::String(s) => s.clone(),
+                PropertyValue::Integer(n) => n.to_string(),
+                PropertyValue::Long(n) => n.to_string(),
+                PropertyValue::Float(n) => n.to_string(),
+                PropertyValue::Double(n) => n.to_string(),
+                PropertyValue::Boolean(b) => if *b { "Y" } else { "N" }.to_string(),
+                PropertyValue::DateTime(dt) => dt.format("%Y-%m-%dT%H:%M:%S").to_string(),
+            };
+            (name.to_string(), value)
+        }));
+        let operations: Vec<FieldOperation> = operations
+            .into_iter()
+            .map(|operation| match operation {
+                FieldOperation::Update(mut context) => {
+                    for (name, value) in &defaults {
+                        context.add_default_property(name, value.clone());
+                    }
+                    FieldOperation::Update(context)
+                },
+                FieldOperation::Flatten => FieldOperation::Flatten,
+            })
+            .collect();
+
+        let doc_uri = PackURI::new("/word/document.xml")
+            .map_err(|e| OoxmlError::InvalidUri(format!("document URI: {}", e)))?;
+        let mut partnames = vec![doc_uri.clone()];
+        for rel in self.opc.get_part(&doc_uri)?.rels().iter() {
+            if !rel.is_external()
+                && [rt::HEADER, rt::FOOTER, rt::FOOTNOTES, rt::ENDNOTES].contains(&rel.reltype())
+                && let Ok(partname) = rel.target_partname()
+            {
+                partnames.push(partname);
+            }
+        }
+
+        for partname in partnames {
+            let Ok(part) = self.opc.get_part_mut(&partname) else {
+                continue;
+            };
+            let mut xml = part.try_blob()?.to_vec();
+            for operation in &operations {
+                xml = apply_field_operation(&xml, operation)?;
+            }
+            part.set_blob(xml);
+        }
+
+        Ok(())
+    }
+
     fn update_theme_part(&mut self, xml: String) -> Result<()> {
         use crate::ooxml::opc::part::BlobPart;
 
@@ -1291,4 +1521,751 @@ mod tests {
         let result = Package::open("test.docx");
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_set_metadata_round_trip() {
+        use crate::common::CustomPropertyValue;
+        use chrono::{TimeZone, Utc};
+        use std::io::Cursor;
+
+        let created = Utc.with_ymd_and_hms(2024, 1, 2, 3, 4, 5).unwrap();
+        let mut metadata = Metadata {
+            title: Some("Quarterly Report".to_string()),
+            author: Some("Jane Doe".to_string()),
+            company: Some("Acme".to_string()),
+            created: Some(created),
+            ..Default::default()
+        };
+        let custom = [
+            ("Client", CustomPropertyValue::String("Globex".to_string())),
+            ("Budget", CustomPropertyValue::Float(1234.5)),
+            ("Pages", CustomPropertyValue::Integer(12)),
+            ("Approved", CustomPropertyValue::Bool(true)),
+            ("Due", CustomPropertyValue::DateTime(created)),
+        ];
+        for (name, value) in custom {
+            metadata.custom_properties.insert(name.to_string(), value);
+        }
+
+        let mut pkg = Package::new().unwrap();
+        pkg.set_metadata(&metadata);
+        let mut bytes = Cursor::new(Vec::new());
+        pkg.to_stream(&mut bytes).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(bytes.get_ref());
+
+        let opc = OpcPackage::from_bytes(bytes.get_ref()).unwrap();
+        let read = crate::ooxml::metadata::extract_metadata(&opc).unwrap();
+        assert_eq!(read.title.as_deref(), Some("Quarterly Report"));
+        assert_eq!(read.author.as_deref(), Some("Jane Doe"));
+        assert_eq!(read.company.as_deref(), Some("Acme"));
+        assert_eq!(read.created, Some(created));
+        assert!(read.modified.is_some());
+        assert_eq!(read.custom_properties, metadata.custom_properties);
+
+        // Reopening keeps the properties for the next save
+        let reopened = Package::from_opc_package(opc).unwrap();
+        assert_eq!(reopened.properties().created, Some(created));
+        assert_eq!(reopened.custom_properties().len(), 5);
+    }
+
+    fn create_docm_with_custom_xml() -> Vec<u8> {
+        use soapberry_zip::office::StreamingArchiveWriter;
+
+        let mut writer = StreamingArchiveWriter::new();
+        writer
+            .write_deflated(
+                "[Content_Types].xml",
+                br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.ms-word.document.macroEnabled.main+xml"/>
+<Override PartName="/word/vbaProject.bin" ContentType="application/vnd.ms-office.vbaProject"/>
+<Override PartName="/word/vbaData.xml" ContentType="application/vnd.ms-word.vbaData+xml"/>
+<Override PartName="/customXml/itemProps1.xml" ContentType="application/vnd.openxmlformats-officedocument.customXmlProperties+xml"/>
+<Override PartName="/word/extension/data.bin" ContentType="application/x-vendor-extension"/>
+</Types>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "_rels/.rels",
+                br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "word/document.xml",
+                br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body><w:p><w:r><w:t>Original</w:t></w:r></w:p></w:body></w:document>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "word/_rels/document.xml.rels",
+                br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId5" Type="http://schemas.microsoft.com/office/2006/relationships/vbaProject" Target="vbaProject.bin"/><Relationship Id="rId9" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml" Target="../customXml/item1.xml"/></Relationships>"#,
+            )
+            .unwrap();
+        writer
+            .write_stored(
+                "word/vbaProject.bin",
+                &[0xD0, 0xCF, 0x11, 0xE0, 0xA1, 0xB1, 0x1A, 0xE1],
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "word/_rels/vbaProject.bin.rels",
+                br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.microsoft.com/office/2006/relationships/wordVbaData" Target="vbaData.xml"/></Relationships>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "word/vbaData.xml",
+                br#"<wne:vbaSuppData xmlns:wne="http://schemas.microsoft.com/office/word/2006/wordml"/>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated("customXml/item1.xml", b"<invoice><id>42</id></invoice>")
+            .unwrap();
+        writer
+            .write_deflated(
+                "customXml/_rels/item1.xml.rels",
+                br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXmlProps" Target="itemProps1.xml"/></Relationships>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "customXml/itemProps1.xml",
+                br#"<ds:datastoreItem ds:itemID="{6A1F}" xmlns:ds="http://schemas.openxmlformats.org/officeDocument/2006/customXml"/>"#,
+            )
+            .unwrap();
+        // Not referenced by any relationship
+        writer
+            .write_deflated("word/extension/data.bin", b"vendor payload")
+            .unwrap();
+        writer.finish_to_bytes().unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_preserves_unknown_parts() {
+        use crate::ooxml::opc::constants::relationship_type as rt;
+        use std::io::Cursor;
+
+        let source = create_docm_with_custom_xml();
+        let original = OpcPackage::from_bytes(&source).unwrap();
+
+        let mut pkg = Package::from_reader(Cursor::new(source.clone())).unwrap();
+        pkg.document_mut().unwrap().add_paragraph_with_text("Added");
+        let mut saved = Cursor::new(Vec::new());
+        pkg.to_stream(&mut saved).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(saved.get_ref());
+
+        let reopened = OpcPackage::from_bytes(saved.get_ref()).unwrap();
+        for partname in [
+            "/word/vbaProject.bin",
+            "/word/vbaData.xml",
+            "/customXml/item1.xml",
+            "/customXml/itemProps1.xml",
+            "/word/extension/data.bin",
+        ] {
+            let partname = PackURI::new(partname).unwrap();
+            let before = original.get_part(&partname).unwrap();
+            let after = reopened.get_part(&partname).unwrap();
+            assert_eq!(after.blob(), before.blob(), "{partname}");
+            assert_eq!(after.content_type(), before.content_type(), "{partname}");
+        }
+
+        let document = reopened.main_document_part().unwrap();
+        assert_eq!(document.content_type(), ct::WML_DOCUMENT_MACRO_MAIN);
+        assert!(
+            std::str::from_utf8(document.blob())
+                .unwrap()
+                .contains("Added")
+        );
+        assert_eq!(
+            reopened.vba_project_part().unwrap().partname().to_string(),
+            "/word/vbaProject.bin"
+        );
+        let vba = document.rels().get("rId5").unwrap();
+        assert_eq!(vba.reltype(), rt::VBA_PROJECT);
+        assert_eq!(vba.target_ref(), "vbaProject.bin");
+        let custom_xml = document.rels().get("rId9").unwrap();
+        assert_eq!(custom_xml.reltype(), rt::CUSTOM_XML);
+        assert_eq!(custom_xml.target_ref(), "../customXml/item1.xml");
+
+        let item = reopened
+            .get_part(&PackURI::new("/customXml/item1.xml").unwrap())
+            .unwrap();
+        assert_eq!(
+            item.rels().get("rId1").unwrap().reltype(),
+            rt::CUSTOM_XML_PROPS
+        );
+
+        // Reopening through the Word API still works for macro-enabled files
+        Package::from_reader(Cursor::new(saved.into_inner())).unwrap();
+    }
+
+    #[test]
+    fn test_fill_form_and_read_back() {
+        use crate::ooxml::docx::writer::ContentControlValue;
+        use chrono::NaiveDate;
+        use std::io::Cursor;
+
+        let mut pkg = Package::open("test-data/ooxml/docx/form.docx").unwrap();
+        let doc = pkg.document_mut().unwrap();
+        let tags: Vec<_> = doc
+            .content_controls()
+            .iter()
+            .map(|c| {
+                (
+                    c.tag().unwrap().to_string(),
+                    c.control_type().map(str::to_string),
+                )
+            })
+            .collect();
+        assert_eq!(
+            tags,
+            [
+                ("name".to_string(), Some("text".to_string())),
+                ("comments".to_string(), None),
+                ("plan".to_string(), Some("dropDownList".to_string())),
+                ("start".to_string(), Some("date".to_string())),
+                ("newsletter".to_string(), Some("checkbox".to_string())),
+            ]
+        );
+
+        // Values that do not suit the control are rejected up front
+        assert!(doc.set_content_control_value("plan", "Premium").is_err());
+        assert!(doc.set_content_control_value("newsletter", "yes").is_err());
+        assert!(doc.set_content_control_value("name", true).is_err());
+        assert_eq!(doc.set_content_control_value("missing", "x").unwrap(), 0);
+
+        let start = NaiveDate::from_ymd_opt(2025, 1, 6).unwrap();
+        assert_eq!(
+            doc.set_content_control_value("name", "Ada <Lovelace>")
+                .unwrap(),
+            1
+        );
+        doc.set_content_control_value("comments", "First line\nSecond line")
+            .unwrap();
+        doc.set_content_control_value("plan", ContentControlValue::Item("Professional".into()))
+            .unwrap();
+        doc.set_content_control_value("start", start).unwrap();
+        doc.set_content_control_value("newsletter", true).unwrap();
+
+        let mut saved = Cursor::new(Vec::new());
+        pkg.to_stream(&mut saved).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(saved.get_ref());
+
+        let reopened = Package::from_reader(Cursor::new(saved.into_inner())).unwrap();
+        let document = reopened.document().unwrap();
+        let controls = document.content_controls().unwrap();
+        assert!(controls.iter().all(|c| !c.is_showing_placeholder()));
+        assert_eq!(controls[0].value(), "Ada <Lovelace>");
+        assert_eq!(controls[1].value(), "First line\nSecond line");
+        assert_eq!(controls[2].value(), "Professional");
+        assert_eq!(controls[3].value(), "6 January 2025");
+        assert_eq!(controls[3].date(), Some(start));
+        assert_eq!(controls[4].is_checked(), Some(true));
+        assert_eq!(controls[4].value(), "\u{2612}");
+
+        // The text around the controls is untouched
+        let text = document.text().unwrap();
+        assert!(text.contains("Registration form"));
+        assert!(text.contains("Subscribe to the newsletter"));
+
+        // The bound custom XML node follows the control
+        let custom_xml = document.custom_xml_parts().unwrap();
+        assert_eq!(custom_xml.len(), 1);
+        assert!(custom_xml[0].xml_content().contains(
+            "<ns0:name>Ada &lt;Lovelace&gt;</ns0:name><ns0:email>ada@example.com</ns0:email>"
+        ));
+    }
+
+    #[test]
+    fn test_update_and_flatten_mail_merge_fields() {
+        use crate::ooxml::docx::writer::FieldContext;
+        use chrono::NaiveDate;
+        use std::io::Cursor;
+
+        let context = FieldContext::new()
+            .with_merge_field("FirstName", "ada")
+            .with_merge_field("LastName", "Lovelace")
+            .with_merge_field("Country", "France")
+            .with_now(
+                NaiveDate::from_ymd_opt(2025, 2, 14)
+                    .unwrap()
+                    .and_hms_opt(9, 30, 0)
+                    .unwrap(),
+            );
+        let save = |flatten: bool| {
+            let mut pkg = Package::open("test-data/ooxml/docx/mail_merge.docx").unwrap();
+            let doc = pkg.document_mut().unwrap();
+            doc.update_fields(context.clone());
+            if flatten {
+                doc.flatten_fields();
+            }
+            let mut saved = Cursor::new(Vec::new());
+            pkg.to_stream(&mut saved).unwrap();
+            crate::ooxml::opc::validation::assert_valid_package(saved.get_ref());
+            saved.into_inner()
+        };
+        let part_xml = |bytes: &[u8], name: &str| {
+            let opc = OpcPackage::from_bytes(bytes).unwrap();
+            let part = opc.get_part(&PackURI::new(name).unwrap()).unwrap();
+            String::from_utf8(part.blob().to_vec()).unwrap()
+        };
+
+        let updated = save(false);
+        let reopened = Package::from_reader(Cursor::new(updated.clone())).unwrap();
+        let results: Vec<(String, Option<String>)> = reopened
+            .document()
+            .unwrap()
+            .fields()
+            .unwrap()
+            .iter()
+            .map(|f| (f.field_type().to_string(), f.result().map(str::to_string)))
+            .collect();
+        let result = |i: usize| results[i].1.as_deref();
+        assert_eq!(results.len(), 6);
+        assert_eq!(result(0), Some("14 February 2025"));
+        assert_eq!(result(1), Some("ada"));
+        // DOCPROPERTY uses the title of the package
+        assert_eq!(result(2), Some("Quarterly update"));
+        // IF compares the result of the nested MERGEFIELD
+        assert_eq!(result(3), Some("Bonjour"));
+        assert_eq!(result(4), Some("1"));
+        assert_eq!(result(5), Some("2"));
+
+        let document = part_xml(&updated, "/word/document.xml");
+        assert!(document.contains("LOVELACE"));
+        // MERGEFORMAT keeps the italic formatting of the old result
+        assert!(
+            document
+                .contains(r#"<w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve">ada</w:t></w:r>"#)
+        );
+        // PAGE cannot be computed and keeps its result
+        assert!(
+            document
+                .contains(r#"<w:fldSimple w:instr=" PAGE "><w:r><w:t>1</w:t></w:r></w:fldSimple>"#)
+        );
+        assert!(part_xml(&updated, "/word/header1.xml").contains(">ada</w:t>"));
+
+        let flattened = save(true);
+        let reopened = Package::from_reader(Cursor::new(flattened.clone())).unwrap();
+        assert!(reopened.document().unwrap().fields().unwrap().is_empty());
+        let document = part_xml(&flattened, "/word/document.xml");
+        for markup in ["fldChar", "instrText", "fldSimple"] {
+            assert!(!document.contains(markup), "{markup}");
+        }
+        for text in [
+            "14 February 2025",
+            "Quarterly update",
+            "Bonjour",
+            "LOVELACE",
+            ">2</w:t>",
+        ] {
+            assert!(document.contains(text), "{text}");
+        }
+        assert!(!part_xml(&flattened, "/word/header1.xml").contains("fldSimple"));
+    }
+
+    #[test]
+    fn test_copy_bookmarked_content_between_documents() {
+        use crate::ooxml::docx::writer::FragmentPosition;
+        use crate::ooxml::opc::constants::relationship_type as rt;
+        use std::io::Cursor;
+
+        let source = Package::open("test-data/ooxml/docx/bookmark_source.docx").unwrap();
+        let source_doc = source.document().unwrap();
+        let clause = source_doc.extract_bookmark_content("clause").unwrap();
+        assert!(!clause.is_inline());
+        assert_eq!(
+            clause.text().unwrap(),
+            "Start text\nClause heading\nList item\nExample link\n\nCell text\nEnd text"
+        );
+        assert_eq!(clause.part_count(), 1);
+        let client = source_doc.extract_bookmark_content("client").unwrap();
+        assert!(client.is_inline());
+        assert!(source_doc.extract_bookmark_content("missing").is_err());
+
+        let save = |clause: crate::ooxml::docx::DocumentFragment| {
+            let mut pkg = Package::open("test-data/ooxml/docx/bookmark_target.docx").unwrap();
+            let doc = pkg.document_mut().unwrap();
+            assert!(
+                doc.insert_fragment_at_bookmark("missing", client.clone(), FragmentPosition::End)
+                    .is_err()
+            );
+            doc.insert_fragment_at_bookmark("name_here", client.clone(), FragmentPosition::Replace)
+                .unwrap();
+            doc.insert_fragment_at_bookmark("terms", clause, FragmentPosition::Start)
+                .unwrap();
+            let mut saved = Cursor::new(Vec::new());
+            pkg.to_stream(&mut saved).unwrap();
+            crate::ooxml::opc::validation::assert_valid_package(saved.get_ref());
+            saved.into_inner()
+        };
+        let part_xml = |bytes: &[u8], name: &str| {
+            let opc = OpcPackage::from_bytes(bytes).unwrap();
+            let part = opc.get_part(&PackURI::new(name).unwrap()).unwrap();
+            String::from_utf8(part.blob().to_vec()).unwrap()
+        };
+
+        let saved = save(clause.clone());
+        let reopened = Package::from_reader(Cursor::new(saved.clone())).unwrap();
+        let texts: Vec<String> = reopened
+            .document()
+            .unwrap()
+            .paragraphs()
+            .unwrap()
+            .iter()
+            .map(|p| p.text().unwrap())
+            .collect();
+        assert_eq!(
+            texts[2..],
+            [
+                "Terms: ",
+                "Start text",
+                "Clause heading",
+                "List item",
+                "Example link",
+                "",
+                "Cell text",
+                "End text",
+                " apply.",
+                "Signature"
+            ]
+        );
+
+        let document = part_xml(&saved, "/word/document.xml");
+        // Drawing namespaces of the source are declared on the root
+        assert!(document.contains("xmlns:pic="));
+        assert!(document.contains(r#"<w:numId w:val="2"/>"#));
+        assert!(document.contains(r#"<w:rStyle w:val="Emphasis2"/>"#));
+        assert!(document.contains(r#"<wp:docPr id="1" name="Picture 1"/>"#));
+        assert!(document.contains(
+            r#"<w:bookmarkStart w:id="0" w:name="name_here"/><w:r><w:t>Acme Corp</w:t></w:r><w:bookmarkEnd w:id="0"/>"#
+        ));
+
+        let styles = part_xml(&saved, "/word/styles.xml");
+        assert_eq!(styles.matches(r#"w:styleId="Heading1""#).count(), 1);
+        assert!(styles.contains("FF0000"));
+        assert!(styles.contains(r#"w:styleId="Callout""#));
+        assert!(styles.contains(r#"w:styleId="Emphasis2""#));
+
+        let numbering = part_xml(&saved, "/word/numbering.xml");
+        assert!(numbering.contains(r#"<w:abstractNum w:abstractNumId="1">"#));
+        assert!(numbering.contains(r#"<w:num w:numId="2"><w:abstractNumId w:val="1"/></w:num>"#));
+        assert!(!numbering.contains("nsid"));
+
+        // The image is copied next to the destination's own image
+        let opc = OpcPackage::from_bytes(&saved).unwrap();
+        let doc_part = opc.main_document_part().unwrap();
+        let images: Vec<String> = doc_part
+            .rels()
+            .iter()
+            .filter(|rel| rel.reltype() == rt::IMAGE)
+            .map(|rel| rel.target_partname().unwrap().to_string())
+            .collect();
+        assert_eq!(images.len(), 2);
+        assert!(images.contains(&"/word/media/image2.png".to_string()));
+        let source_opc = OpcPackage::open("test-data/ooxml/docx/bookmark_source.docx").unwrap();
+        let original = PackURI::new("/word/media/image1.png").unwrap();
+        let copied = PackURI::new("/word/media/image2.png").unwrap();
+        assert_eq!(
+            opc.get_part(&copied).unwrap().blob(),
+            source_opc.get_part(&original).unwrap().blob()
+        );
+        assert!(
+            doc_part
+                .rels()
+                .iter()
+                .any(|rel| rel.reltype() == rt::HYPERLINK
+                    && rel.is_external()
+                    && rel.target_ref() == "https://example.com/terms")
+        );
+
+        // Forcing the source styles replaces the destination definition
+        let forced = save(clause.with_source_styles(true));
+        let styles = part_xml(&forced, "/word/styles.xml");
+        assert_eq!(styles.matches(r#"w:styleId="Heading1""#).count(), 1);
+        assert!(styles.contains("0000FF"));
+        assert!(!styles.contains("FF0000"));
+    }
+
+    #[test]
+    fn test_write_headers_footers_and_page_numbers() {
+        use crate::ooxml::docx::writer::{MutableParagraph, PageNumberFormat};
+        use std::io::Cursor;
+
+        let mut pkg = Package::new().unwrap();
+        let doc = pkg.document_mut().unwrap();
+        doc.add_paragraph_with_text("Body");
+        doc.add_header_paragraph()
+            .add_run_with_text("Quarterly Report");
+        let mut cover = MutableParagraph::new();
+        cover.add_run_with_text("Confidential");
+        doc.set_header(WdHeaderFooter::FirstPage, vec![cover]);
+        let mut page = MutableParagraph::new();
+        page.add_page_x_of_y(PageNumberFormat::LowerRoman);
+        doc.set_footer(WdHeaderFooter::EvenPage, vec![page]);
+
+        let mut saved = Cursor::new(Vec::new());
+        pkg.to_stream(&mut saved).unwrap();
+        let saved = saved.into_inner();
+        crate::ooxml::opc::validation::assert_valid_package(&saved);
+
+        let reopened = Package::from_reader(Cursor::new(saved)).unwrap();
+        let document = reopened.document().unwrap();
+        let header = |kind| document.header(kind).unwrap().map(|h| h.text().unwrap());
+        assert_eq!(
+            header(WdHeaderFooter::Primary).as_deref(),
+            Some("Quarterly Report")
+        );
+        assert_eq!(
+            header(WdHeaderFooter::FirstPage).as_deref(),
+            Some("Confidential")
+        );
+        assert_eq!(header(WdHeaderFooter::EvenPage), None);
+        assert!(document.footer(WdHeaderFooter::Primary).unwrap().is_none());
+
+        // The section references every part by kind
+        let sections = document.headers_by_section().unwrap();
+        let kinds: Vec<_> = sections[0].iter().map(|(kind, _)| *kind).collect();
+        assert_eq!(kinds, [WdHeaderFooter::Primary, WdHeaderFooter::FirstPage]);
+        let footers = document.footers_by_section().unwrap();
+        assert_eq!(footers[0].len(), 1);
+        assert_eq!(footers[0][0].0, WdHeaderFooter::EvenPage);
+
+        let opc = &reopened.opc;
+        let part_xml = |name: &str| {
+            let uri = PackURI::new(name).unwrap();
+            String::from_utf8(opc.get_part(&uri).unwrap().blob().to_vec()).unwrap()
+        };
+        let footer = part_xml("/word/footer3.xml");
+        assert!(footer.contains("PAGE \\* roman"));
+        assert!(footer.contains("NUMPAGES"));
+        assert!(footer.contains(">Page </w:t>"));
+        let body = part_xml("/word/document.xml");
+        assert!(body.contains("<w:titlePg/>"));
+        assert!(body.contains(r#"<w:footerReference w:type="even""#));
+        assert!(part_xml("/word/settings.xml").contains("<w:evenAndOddHeaders/>"));
+    }
+
+    #[test]
+    fn test_write_sections_and_read_back() {
+        use crate::markdown::ToMarkdown;
+        use crate::ooxml::docx::enums::WdOrientation;
+        use crate::ooxml::docx::section::Emu;
+        use crate::ooxml::docx::writer::{PageNumberFormat, SectionProperties};
+        use std::io::Cursor;
+
+        let mut pkg = Package::new().unwrap();
+        let doc = pkg.document_mut().unwrap();
+        doc.section_mut().page_number_format = Some(PageNumberFormat::LowerRoman);
+        doc.add_paragraph_with_text("Summary");
+        doc.insert_section_break(
+            SectionProperties::letter()
+                .landscape()
+                .margins(0.5, 0.5, 0.5, 0.5)
+                .restart_page_numbering(1),
+        );
+        let table = doc.add_table(1, 2);
+        table.cell(0, 0).unwrap().set_text("Wide");
+        table.cell(0, 1).unwrap().set_text("Table");
+        doc.insert_section_break(SectionProperties::letter().columns(2, 0.25));
+        doc.add_paragraph_with_text("Appendix");
+        doc.add_footer_paragraph()
+            .add_page_number_field(PageNumberFormat::Decimal);
+
+        let mut saved = Cursor::new(Vec::new());
+        pkg.to_stream(&mut saved).unwrap();
+        let saved = saved.into_inner();
+        crate::ooxml::opc::validation::assert_valid_package(&saved);
+
+        let reopened = Package::from_reader(Cursor::new(saved.clone())).unwrap();
+        let document = reopened.document().unwrap();
+        let mut sections = document.sections().unwrap();
+        assert_eq!(sections.len(), 3);
+
+        let first = &mut sections[0];
+        assert_eq!(first.orientation(), WdOrientation::Portrait);
+        assert_eq!(first.page_number_format().as_deref(), Some("lowerRoman"));
+        assert_eq!(first.page_number_start(), None);
+
+        let wide = &mut sections[1];
+        assert_eq!(wide.orientation(), WdOrientation::Landscape);
+        assert_eq!(wide.page_width(), Some(Emu::from_twips(15840)));
+        assert_eq!(wide.top_margin(), Some(Emu::from_twips(720)));
+        assert_eq!(wide.page_number_start(), Some(1));
+        assert_eq!(wide.column_count(), 1);
+
+        let last = &mut sections[2];
+        assert_eq!(last.orientation(), WdOrientation::Portrait);
+        assert_eq!(last.column_count(), 2);
+        assert_eq!(last.column_spacing(), Some(Emu::from_twips(360)));
+
+        // Every section shows the footer
+        let footers = document.footers_by_section().unwrap();
+        assert!(footers.iter().all(|footers| footers.len() == 1));
+
+        let markdown = crate::Document::from_bytes(saved)
+            .unwrap()
+            .to_markdown()
+            .unwrap();
+        assert!(markdown.contains("Summary"));
+        assert!(markdown.contains("| Wide | Table |"));
+        assert!(markdown.contains("Appendix"));
+    }
+
+    #[test]
+    fn test_named_styles_and_effective_formatting() {
+        use crate::common::RGBColor;
+        use crate::ooxml::docx::enums::WdStyleType;
+        use crate::ooxml::docx::writer::MutableStyle;
+        use crate::ooxml::drawings::color::ColorScheme;
+        use std::io::Cursor;
+
+        let mut pkg = Package::new().unwrap();
+        let doc = pkg.document_mut().unwrap();
+
+        let mut corporate = MutableStyle::new("Corporate", "Corporate", WdStyleType::Paragraph);
+        corporate.set_based_on(Some("Normal".to_string()));
+        corporate.set_font_name(Some("Georgia".to_string()));
+        corporate.set_bold(true);
+        corporate.set_color(Some("1F3864".to_string()));
+        let mut body = MutableStyle::new("CorporateBody", "Corporate Body", WdStyleType::Paragraph);
+        body.set_based_on(Some("Corporate".to_string()));
+        body.set_font_size(Some(20));
+        body.set_link(Some("CorporateBodyChar".to_string()));
+        let mut small = MutableStyle::new("BodySmall", "Body Small", WdStyleType::Paragraph);
+        small.set_based_on(Some("CorporateBody".to_string()));
+        small.set_next(Some("CorporateBody".to_string()));
+        small.set_bold(false);
+        small.set_font_size(Some(16));
+        small.set_space_after(Some(60));
+        let mut accent = MutableStyle::new("Accent", "Accent", WdStyleType::Character);
+        accent.set_italic(true);
+        accent.set_color(Some("C00000".to_string()));
+        // Replacing a built-in style keeps a single definition
+        let mut normal = MutableStyle::normal();
+        normal.set_font_size(Some(24));
+        for style in [corporate, body, small, accent, normal] {
+            doc.add_style(style);
+        }
+
+        doc.add_paragraph_with_text("Heading text")
+            .set_style("Corporate");
+        let para = doc.add_paragraph();
+        para.set_style("BodySmall");
+        para.add_run_with_text("fine print ");
+        para.add_run_with_text("accented").style("Accent");
+        para.add_run_with_text(" large").font_size(30).bold(true);
+        doc.add_paragraph_with_text("Plain");
+
+        let mut saved = Cursor::new(Vec::new());
+        pkg.to_stream(&mut saved).unwrap();
+        let saved = saved.into_inner();
+        crate::ooxml::opc::validation::assert_valid_package(&saved);
+
+        let reopened = Package::from_reader(Cursor::new(saved)).unwrap();
+        let document = reopened.document().unwrap();
+        let mut styles = document.styles().unwrap();
+        assert_eq!(
+            styles
+                .iter()
+                .unwrap()
+                .filter(|s| s.style_id() == "Normal")
+                .count(),
+            1
+        );
+        let small = styles.get_by_id("BodySmall").unwrap().unwrap();
+        assert_eq!(small.based_on(), Some("CorporateBody"));
+        assert_eq!(small.next_style(), Some("CorporateBody"));
+        assert_eq!(
+            styles
+                .get_by_name("Corporate Body")
+                .unwrap()
+                .unwrap()
+                .linked_style(),
+            Some("CorporateBodyChar")
+        );
+
+        let scheme = ColorScheme::default();
+        let paragraphs = document.paragraphs().unwrap();
+        let heading = &paragraphs[0];
+        let run = &heading.runs().unwrap()[0];
+        assert_eq!(run.bold().unwrap(), None);
+        assert!(run.effective_bold(heading, &mut styles).unwrap());
+        assert_eq!(run.effective_font_size(heading, &mut styles).unwrap(), 24);
+        assert_eq!(
+            run.effective_format(heading, &mut styles)
+                .unwrap()
+                .font_name
+                .as_deref(),
+            Some("Georgia")
+        );
+
+        // BodySmall < CorporateBody < Corporate < Normal
+        let para = &paragraphs[1];
+        let runs = para.runs().unwrap();
+        assert!(!runs[0].effective_bold(para, &mut styles).unwrap());
+        assert_eq!(runs[0].effective_font_size(para, &mut styles).unwrap(), 16);
+        assert_eq!(
+            runs[0].effective_color(para, &mut styles, &scheme).unwrap(),
+            Some(RGBColor::new(0x1F, 0x38, 0x64))
+        );
+        assert!(runs[1].effective_italic(para, &mut styles).unwrap());
+        assert_eq!(
+            runs[1].effective_color(para, &mut styles, &scheme).unwrap(),
+            Some(RGBColor::new(0xC0, 0x00, 0x00))
+        );
+        assert!(runs[2].effective_bold(para, &mut styles).unwrap());
+        assert_eq!(runs[2].effective_font_size(para, &mut styles).unwrap(), 30);
+
+        let plain = &paragraphs[2];
+        let run = &plain.runs().unwrap()[0];
+        assert!(!run.effective_bold(plain, &mut styles).unwrap());
+        assert_eq!(run.effective_font_size(plain, &mut styles).unwrap(), 24);
+        assert_eq!(
+            run.effective_format(plain, &mut styles)
+                .unwrap()
+                .font_name
+                .as_deref(),
+            Some("Calibri")
+        );
+    }
+
+    #[cfg(feature = "fonts")]
+    #[test]
+    fn test_embed_registered_font() {
+        use std::io::Cursor;
+
+        let Ok(font) = crate::fonts::FontLoader::new().load_system_font("sans-serif") else {
+            // No font installed to register
+            return;
+        };
+
+        let mut pkg = Package::new().unwrap();
+        pkg.document_mut()
+            .unwrap()
+            .add_paragraph()
+            .add_run_with_text("Shipped with the app")
+            .font_name("Corporate Sans");
+        pkg.opc_package_mut()
+            .with_font_embedding(true, true)
+            .register_font("Corporate Sans", font.data.clone());
+        let mut bytes = Cursor::new(Vec::new());
+        pkg.to_stream(&mut bytes).unwrap();
+
+        let opc = OpcPackage::from_bytes(bytes.get_ref()).unwrap();
+        let uri = PackURI::new("/word/fonts/Corporate_Sans.odttf").unwrap();
+        let embedded = opc.get_part(&uri).unwrap().blob();
+        assert!(!embedded.is_empty());
+        assert!(embedded.len() < font.data.len());
+    }
 }
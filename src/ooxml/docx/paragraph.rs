@@ -1,14 +1,20 @@
-/// Paragraph and Run structures for Word documents.
-use crate::common::VerticalPosition;
 use crate::common::XmlSlice;
+/// Paragraph and Run structures for Word documents.
+use crate::common::{Language, RGBColor, VerticalPosition};
+use crate::document::{ListInfo, RevisionMode};
+use crate::ooxml::docx::content_control::push_general_ref;
 use crate::ooxml::docx::drawing::{DrawingObject, parse_drawing_objects};
 use crate::ooxml::docx::hyperlink::Hyperlink;
 use crate::ooxml::docx::image::{InlineImage, parse_inline_images};
-use crate::ooxml::docx::revision::{Revision, parse_revisions};
+use crate::ooxml::docx::revision::{Revision, parse_revisions, revision_text};
+use crate::ooxml::docx::styles::{CharacterFormat, StyleLanguages, Styles, parse_lang};
+use crate::ooxml::docx::text_box::{TextBox, parse_text_boxes};
+use crate::ooxml::drawings::color::{ColorScheme, ColorSpec, ThemeColorSlot, word_tint_shade};
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::rel::Relationships;
+use crate::ooxml::pptx::smartart::diagram_rel_ids;
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::sync::Arc;
@@ -63,6 +69,10 @@ impl XmlData {
 pub struct Paragraph {
     /// The raw XML bytes for this paragraph
     xml_data: XmlData,
+    /// List numbering, computed by the document for body paragraphs
+    list_info: Option<ListInfo>,
+    /// Style languages, attached by the document to body paragraphs
+    style_languages: Option<Arc<StyleLanguages>>,
 }
 
 impl Paragraph {
@@ -75,6 +85,8 @@ impl Paragraph {
     pub fn new(xml_bytes: Vec<u8>) -> Self {
         Self {
             xml_data: XmlData::Owned(xml_bytes.into_boxed_slice()),
+            list_info: None,
+            style_languages: None,
         }
     }
 
@@ -86,6 +98,8 @@ impl Paragraph {
     pub fn from_slice(slice: XmlSlice) -> Self {
         Self {
             xml_data: XmlData::Shared(slice),
+            list_info: None,
+            style_languages: None,
         }
     }
 
@@ -105,7 +119,9 @@ impl Paragraph {
 
     /// Get the text content of this paragraph.
     ///
-    /// Concatenates all text from all runs in the paragraph.
+    /// Concatenates all text from all runs in the paragraph. Text boxes
+    /// anchored in the paragraph are left out; see
+    /// [`text_boxes`](Self::text_boxes).
     ///
     /// # Performance
     ///
@@ -113,7 +129,7 @@ impl Paragraph {
     pub fn text(&self) -> Result<String> {
         let xml_bytes = self.xml_bytes();
         let mut reader = Reader::from_reader(xml_bytes);
-        reader.config_mut().trim_text(true);
+        reader.config_mut().trim_text(false);
 
         // Pre-allocate string with estimated capacity to reduce reallocations
         let estimated_capacity = xml_bytes.len() / 4; // Rough estimate
@@ -122,9 +138,23 @@ impl Paragraph {
 
         loop {
             match reader.read_event() {
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"txbxContent" => {
+                    // Text box content is reported by `text_boxes`
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                },
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"pPr" => {
+                    // Tab stops (`w:tabs/w:tab`) are not text
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                },
                 Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                     if e.local_name().as_ref() == b"t" {
                         in_text_element = true;
+                    } else if let Some(ch) = run_content_char(&e, false) {
+                        result.push(ch);
                     }
                 },
                 Ok(Event::Text(e)) if in_text_element => {
@@ -132,6 +162,7 @@ impl Paragraph {
                     let text = unsafe { std::str::from_utf8_unchecked(e.as_ref()) };
                     result.push_str(text);
                 },
+                Ok(Event::GeneralRef(e)) if in_text_element => push_general_ref(&mut result, &e)?,
                 Ok(Event::End(e)) => {
                     if e.local_name().as_ref() == b"t" {
                         in_text_element = false;
@@ -151,6 +182,8 @@ impl Paragraph {
     /// Get an iterator over the runs in this paragraph.
     ///
     /// Each run represents a `<w:r>` element and may have different formatting.
+    /// Equations (`<m:oMath>`) are returned as runs of their own, in document
+    /// order; see [`Run::is_math`].
     ///
     /// # Performance
     ///
@@ -185,40 +218,41 @@ impl Paragraph {
         let mut write_idx = 0usize;
         let mut i = 0usize;
         let runs_ptr = runs.as_mut_ptr();
+        // Equations inside <m:oMathPara> are display math
+        let mut in_math_para = false;
 
         while i < len && write_idx < count {
             let Some(tag_start) = memchr::memchr(b'<', &xml_bytes[i..]) else {
                 break;
             };
             let tag_start = i + tag_start;
-
-            if tag_start + 5 < len && &xml_bytes[tag_start..tag_start + 4] == b"<w:r" {
-                let next_char = xml_bytes[tag_start + 4];
-                if (next_char == b'>' || next_char == b' ' || next_char == b'/')
-                    && let Some(end) = find_run_end(&xml_bytes[tag_start..])
-                {
-                    let end_pos = tag_start + end;
-                    let run_len = (end_pos - tag_start) as u32;
-
-                    // SAFETY: arc_ptr is valid; each from_raw consumes one refcount we pre-incremented
-                    let arc_clone = unsafe { Arc::from_raw(arc_ptr) };
-
-                    // Write directly to pre-allocated slot (no push)
-                    // Add base_offset to get absolute position in source Arc
-                    unsafe {
-                        std::ptr::write(
-                            runs_ptr.add(write_idx),
-                            Run::from_slice(XmlSlice::new(
-                                arc_clone,
-                                base_offset + tag_start as u32,
-                                run_len,
-                            )),
-                        );
-                    }
-                    write_idx += 1;
-                    i = end_pos;
-                    continue;
+            let tag = &xml_bytes[tag_start..];
+
+            if tag.starts_with(b"<m:oMathPara") {
+                in_math_para = true;
+            } else if tag.starts_with(b"</m:oMathPara") {
+                in_math_para = false;
+            } else if let Some(end) = run_end(tag) {
+                let end_pos = tag_start + end;
+                let run_len = (end_pos - tag_start) as u32;
+
+                // SAFETY: arc_ptr is valid; each from_raw consumes one refcount we pre-incremented
+                let arc_clone = unsafe { Arc::from_raw(arc_ptr) };
+                let mut run = Run::from_slice(XmlSlice::new(
+                    arc_clone,
+                    base_offset + tag_start as u32,
+                    run_len,
+                ));
+                run.display_math = in_math_para && run.is_math();
+
+                // Write directly to pre-allocated slot (no push)
+                // Add base_offset to get absolute position in source Arc
+                unsafe {
+                    std::ptr::write(runs_ptr.add(write_idx), run);
                 }
+                write_idx += 1;
+                i = end_pos;
+                continue;
             }
 
             i = tag_start + 1;
@@ -238,13 +272,23 @@ impl Paragraph {
             runs.set_len(write_idx);
         }
 
+        if let Some(styles) = &self.style_languages {
+            let paragraph = Arc::new(styles.paragraph(self.style_id()?.as_deref()).clone());
+            for run in runs.iter_mut() {
+                run.inherited_language = Some(InheritedLanguage {
+                    styles: Arc::clone(styles),
+                    paragraph: Arc::clone(&paragraph),
+                });
+            }
+        }
+
         Ok(runs)
     }
 
     /// Extract all OMML formulas from this paragraph.
     ///
-    /// Returns a vector of OMML formula strings found in any run within this paragraph.
-    /// This extracts inline formulas (formulas within runs).
+    /// Returns a vector of OMML formula strings found in any run within this paragraph,
+    /// covering both inline and display equations.
     pub fn omml_formulas(&self) -> Result<Vec<String>> {
         let mut formulas = Vec::new();
         for run in self.runs()? {
@@ -255,6 +299,192 @@ impl Paragraph {
         Ok(formulas)
     }
 
+    /// Check whether this paragraph contains an equation.
+    #[inline]
+    pub fn has_math(&self) -> bool {
+        memchr::memmem::find(self.xml_bytes(), b"<m:oMath").is_some()
+    }
+
+    /// Get the IDs of the footnotes referenced from this paragraph.
+    ///
+    /// Returns the `w:id` of every `<w:footnoteReference>` in document order.
+    pub fn footnote_reference_ids(&self) -> Result<Vec<u32>> {
+        self.note_reference_ids(b"footnoteReference")
+    }
+
+    /// Get the IDs of the endnotes referenced from this paragraph.
+    ///
+    /// Returns the `w:id` of every `<w:endnoteReference>` in document order.
+    pub fn endnote_reference_ids(&self) -> Result<Vec<u32>> {
+        self.note_reference_ids(b"endnoteReference")
+    }
+
+    /// Get the ID of the paragraph style (`w:pStyle`), if one is applied.
+    pub fn style_id(&self) -> Result<Option<String>> {
+        self.paragraph_property_val(&[b"pStyle"])
+    }
+
+    /// Get the outline level set directly on this paragraph (`w:outlineLvl`).
+    ///
+    /// Levels are 0-based: 0 is the outline level of "Heading 1". Level 9
+    /// means body text. Returns `None` if the level is inherited from the style.
+    pub fn outline_level(&self) -> Result<Option<u8>> {
+        Ok(self
+            .paragraph_property_val(&[b"outlineLvl"])?
+            .and_then(|value| value.parse().ok()))
+    }
+
+    /// Get the numbering instance set directly on this paragraph (`w:numId`).
+    ///
+    /// A value of 0 removes numbering inherited from the style. Returns `None`
+    /// if numbering is inherited from the style.
+    pub fn num_id(&self) -> Result<Option<u32>> {
+        Ok(self
+            .paragraph_property_val(&[b"numPr", b"numId"])?
+            .and_then(|value| value.parse().ok()))
+    }
+
+    /// Get the list level set directly on this paragraph (`w:ilvl`), 0-based.
+    pub fn numbering_level(&self) -> Result<Option<u8>> {
+        Ok(self
+            .paragraph_property_val(&[b"numPr", b"ilvl"])?
+            .and_then(|value| value.parse().ok()))
+    }
+
+    /// Get the list numbering of this paragraph.
+    ///
+    /// Only set on body paragraphs returned by
+    /// [`Document::paragraphs`](crate::Document::paragraphs) and
+    /// [`Document::elements`](crate::Document::elements), since the label
+    /// depends on the list items before the paragraph.
+    #[inline]
+    pub fn list_info(&self) -> Option<&ListInfo> {
+        self.list_info.as_ref()
+    }
+
+    /// Set the list numbering computed by the document.
+    #[inline]
+    pub(crate) fn set_list_info(&mut self, list_info: Option<ListInfo>) {
+        self.list_info = list_info;
+    }
+
+    /// Attach the style languages used to resolve the languages of the runs.
+    #[inline]
+    pub(crate) fn set_style_languages(&mut self, styles: Arc<StyleLanguages>) {
+        self.style_languages = Some(styles);
+    }
+
+    /// Check whether the paragraph is right-to-left (`w:bidi`).
+    ///
+    /// Returns `None` if the direction is inherited from the style.
+    pub fn bidi(&self) -> Result<Option<bool>> {
+        self.paragraph_property(&[b"bidi"], |e| {
+            Self::val_attribute(e).is_none_or(|value| matches!(value.as_str(), "true" | "1" | "on"))
+        })
+    }
+
+    /// Read the `w:val` of an element nested in the paragraph properties.
+    ///
+    /// `path` lists the element names below `w:pPr`. Properties recorded in a
+    /// tracked change (`w:pPrChange`) are ignored.
+    fn paragraph_property_val(&self, path: &[&[u8]]) -> Result<Option<String>> {
+        Ok(self
+            .paragraph_property(path, Self::val_attribute)?
+            .flatten())
+    }
+
+    /// Read an element nested in the paragraph properties with `read`.
+    ///
+    /// Returns `None` if the element is not present.
+    fn paragraph_property<T>(
+        &self,
+        path: &[&[u8]],
+        read: impl FnOnce(&BytesStart<'_>) -> T,
+    ) -> Result<Option<T>> {
+        let mut reader = Reader::from_reader(self.xml_bytes());
+        let mut depth = 0usize;
+        let mut in_p_pr = false;
+        // Number of leading `path` elements currently open
+        let mut matched = 0usize;
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    depth += 1;
+                    let name = e.local_name();
+                    if depth == 2 {
+                        // pPr is a direct child of the paragraph and always comes first
+                        if name.as_ref() != b"pPr" {
+                            break;
+                        }
+                        in_p_pr = true;
+                    } else if in_p_pr
+                        && depth == 3 + matched
+                        && path.get(matched) == Some(&name.as_ref())
+                    {
+                        if matched + 1 == path.len() {
+                            return Ok(Some(read(&e)));
+                        }
+                        matched += 1;
+                    }
+                },
+                Ok(Event::Empty(e))
+                    if in_p_pr
+                        && depth == 2 + matched
+                        && matched + 1 == path.len()
+                        && path[matched] == e.local_name().as_ref() =>
+                {
+                    return Ok(Some(read(&e)));
+                },
+                Ok(Event::End(_)) => {
+                    if in_p_pr && depth == 2 {
+                        break;
+                    }
+                    if matched > 0 && depth == 2 + matched {
+                        matched -= 1;
+                    }
+                    depth = depth.saturating_sub(1);
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(None)
+    }
+
+    #[inline]
+    fn val_attribute(e: &quick_xml::events::BytesStart<'_>) -> Option<String> {
+        e.attributes()
+            .flatten()
+            .find(|attr| attr.key.local_name().as_ref() == b"val")
+            .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+    }
+
+    fn note_reference_ids(&self, tag: &[u8]) -> Result<Vec<u32>> {
+        let mut reader = Reader::from_reader(self.xml_bytes());
+        let mut ids = Vec::new();
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e) | Event::Empty(e)) if e.local_name().as_ref() == tag => {
+                    let id = e
+                        .attributes()
+                        .flatten()
+                        .find(|attr| attr.key.local_name().as_ref() == b"id")
+                        .and_then(|attr| atoi_simd::parse::<u32, false, false>(&attr.value).ok());
+                    ids.extend(id);
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(ids)
+    }
+
     /// Extract all inline images from this paragraph.
     ///
     /// Returns a vector of `InlineImage` objects found in `<w:drawing>` elements
@@ -303,6 +533,23 @@ impl Paragraph {
         parse_drawing_objects(self.xml_bytes())
     }
 
+    /// Extract the text boxes anchored in this paragraph.
+    ///
+    /// Covers DrawingML shapes with text (`<wps:txbx>`) and VML text boxes
+    /// (`<v:textbox>`); the VML copy Word writes as a fallback for each
+    /// DrawingML shape is not reported again. Text box content is not part of
+    /// [`text`](Self::text) or the text of any run.
+    #[inline]
+    pub fn text_boxes(&self) -> Result<Vec<TextBox>> {
+        parse_text_boxes(self.xml_bytes())
+    }
+
+    /// Get the data part relationship IDs of the SmartArt diagrams anchored
+    /// in this paragraph.
+    pub(crate) fn diagram_rel_ids(&self) -> Result<Vec<String>> {
+        diagram_rel_ids(self.xml_bytes())
+    }
+
     /// Extract all tracked changes (revisions) from this paragraph.
     ///
     /// Returns a vector of `Revision` objects representing all tracked changes
@@ -326,6 +573,15 @@ impl Paragraph {
         parse_revisions(self.xml_bytes())
     }
 
+    /// Get the text of this paragraph with tracked changes materialized.
+    ///
+    /// Unlike [`text`](Self::text), which keeps moved-away text, this follows
+    /// `mode` for every insertion, deletion and move.
+    #[inline]
+    pub fn text_with_revisions(&self, mode: RevisionMode) -> Result<String> {
+        revision_text(self.xml_bytes(), mode, false)
+    }
+
     /// Extract paragraph-level OMML formulas.
     ///
     /// Returns a vector of OMML formula strings that are direct children of the paragraph
@@ -592,10 +848,22 @@ impl RunXmlData {
     }
 }
 
+/// Languages a run inherits from its styles, attached by the paragraph.
+#[derive(Debug, Clone)]
+struct InheritedLanguage {
+    styles: Arc<StyleLanguages>,
+    /// Languages of the paragraph style and the document defaults
+    paragraph: Arc<Language>,
+}
+
 #[derive(Debug, Clone)]
 pub struct Run {
     /// The raw XML data for this run
     xml_data: RunXmlData,
+    /// Whether this is an equation wrapped in `<m:oMathPara>`
+    display_math: bool,
+    /// Languages inherited from styles, for runs of body paragraphs
+    inherited_language: Option<InheritedLanguage>,
 }
 
 impl Run {
@@ -603,6 +871,8 @@ impl Run {
     pub fn new(xml_bytes: Vec<u8>) -> Self {
         Self {
             xml_data: RunXmlData::Owned(xml_bytes),
+            display_math: false,
+            inherited_language: None,
         }
     }
 
@@ -611,6 +881,8 @@ impl Run {
     pub fn from_slice(slice: XmlSlice) -> Self {
         Self {
             xml_data: RunXmlData::Shared(slice),
+            display_math: false,
+            inherited_language: None,
         }
     }
 
@@ -624,11 +896,26 @@ impl Run {
     ///
     /// Extracts text from `<w:t>` elements and converts special characters:
     /// - `<w:tab/>` → tab character
-    /// - `<w:br/>` → newline character
+    /// - `<w:br/>` and `<w:cr/>` → newline character
+    /// - `<w:noBreakHyphen/>` → non-breaking hyphen (U+2011)
+    /// - `<w:softHyphen/>` is dropped, as it only marks an optional break
     pub fn text(&self) -> Result<String> {
+        self.text_with_breaks(false)
+    }
+
+    /// Get the text content of this run, with page breaks
+    /// (`<w:br w:type="page"/>`) as form feeds (`'\x0c'`).
+    ///
+    /// This matches the text of .doc runs, which keep page breaks as form
+    /// feed characters. Other breaks are newlines, as in [`Run::text`].
+    pub fn text_with_page_breaks(&self) -> Result<String> {
+        self.text_with_breaks(true)
+    }
+
+    fn text_with_breaks(&self, page_breaks: bool) -> Result<String> {
         let xml_bytes = self.xml_bytes();
         let mut reader = Reader::from_reader(xml_bytes);
-        reader.config_mut().trim_text(true);
+        reader.config_mut().trim_text(false);
 
         // Pre-allocate with estimated capacity
         let estimated_capacity = xml_bytes.len() / 8; // Rough estimate for text content
@@ -637,14 +924,18 @@ impl Run {
 
         loop {
             match reader.read_event() {
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"txbxContent" => {
+                    // Text box content is reported by `Paragraph::text_boxes`
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                },
                 Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                     let name = e.local_name();
                     if name.as_ref() == b"t" {
                         in_text_element = true;
-                    } else if name.as_ref() == b"tab" {
-                        result.push('\t');
-                    } else if name.as_ref() == b"br" {
-                        result.push('\n');
+                    } else if let Some(ch) = run_content_char(&e, page_breaks) {
+                        result.push(ch);
                     }
                 },
                 Ok(Event::Text(e)) if in_text_element => {
@@ -652,6 +943,7 @@ impl Run {
                     let text = unsafe { std::str::from_utf8_unchecked(e.as_ref()) };
                     result.push_str(text);
                 },
+                Ok(Event::GeneralRef(e)) if in_text_element => push_general_ref(&mut result, &e)?,
                 Ok(Event::End(e)) => {
                     if e.local_name().as_ref() == b"t" {
                         in_text_element = false;
@@ -729,6 +1021,13 @@ impl Run {
         self.get_bool_property(b"strike")
     }
 
+    /// Check if this run is right-to-left (`w:rtl`).
+    ///
+    /// Returns `None` if not specified.
+    pub fn rtl(&self) -> Result<Option<bool>> {
+        self.get_bool_property(b"rtl")
+    }
+
     /// Get text and properties in a single XML parse.
     ///
     /// This is **the fastest way** to extract both text content and formatting properties
@@ -754,7 +1053,7 @@ impl Run {
     /// ```
     pub fn get_text_and_properties(&self) -> Result<(String, RunProperties)> {
         let mut reader = Reader::from_reader(self.xml_bytes());
-        reader.config_mut().trim_text(true);
+        reader.config_mut().trim_text(false);
 
         let mut props = RunProperties::default();
         let mut text = String::with_capacity(self.xml_bytes().len() / 8);
@@ -763,16 +1062,20 @@ impl Run {
 
         loop {
             match reader.read_event() {
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"txbxContent" => {
+                    // Text box content is reported by `Paragraph::text_boxes`
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                },
                 Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                     let name = e.local_name();
 
                     // Handle text elements
                     if name.as_ref() == b"t" {
                         in_text_element = true;
-                    } else if name.as_ref() == b"tab" {
-                        text.push('\t');
-                    } else if name.as_ref() == b"br" {
-                        text.push('\n');
+                    } else if let Some(ch) = run_content_char(&e, false) {
+                        text.push(ch);
                     } else if name.as_ref() == b"rPr" {
                         in_r_pr = true;
                     } else if in_r_pr {
@@ -781,7 +1084,7 @@ impl Run {
                             b"b" => {
                                 let mut found_val = false;
                                 for attr in e.attributes().flatten() {
-                                    if attr.key.as_ref() == b"val" {
+                                    if attr.key.local_name().as_ref() == b"val" {
                                         found_val = true;
                                         let value = attr.value.as_ref();
                                         props.bold = Some(value == b"true" || value == b"1");
@@ -795,7 +1098,7 @@ impl Run {
                             b"i" => {
                                 let mut found_val = false;
                                 for attr in e.attributes().flatten() {
-                                    if attr.key.as_ref() == b"val" {
+                                    if attr.key.local_name().as_ref() == b"val" {
                                         found_val = true;
                                         let value = attr.value.as_ref();
                                         props.italic = Some(value == b"true" || value == b"1");
@@ -809,7 +1112,7 @@ impl Run {
                             b"strike" => {
                                 let mut found_val = false;
                                 for attr in e.attributes().flatten() {
-                                    if attr.key.as_ref() == b"val" {
+                                    if attr.key.local_name().as_ref() == b"val" {
                                         found_val = true;
                                         let value = attr.value.as_ref();
                                         props.strikethrough =
@@ -823,7 +1126,7 @@ impl Run {
                             },
                             b"vertAlign" => {
                                 for attr in e.attributes().flatten() {
-                                    if attr.key.as_ref() == b"val" {
+                                    if attr.key.local_name().as_ref() == b"val" {
                                         let value = attr.value.as_ref();
                                         props.vertical_position = match value {
                                             b"superscript" => Some(VerticalPosition::Superscript),
@@ -844,6 +1147,7 @@ impl Run {
                         text.push_str(text_str);
                     }
                 },
+                Ok(Event::GeneralRef(e)) if in_text_element => push_general_ref(&mut text, &e)?,
                 Ok(Event::End(e)) => {
                     let name = e.local_name();
                     if name.as_ref() == b"t" {
@@ -906,7 +1210,7 @@ impl Run {
                                 // Check for w:val attribute
                                 let mut found_val = false;
                                 for attr in e.attributes().flatten() {
-                                    if attr.key.as_ref() == b"val" {
+                                    if attr.key.local_name().as_ref() == b"val" {
                                         found_val = true;
                                         let value = attr.value.as_ref();
                                         props.bold = Some(value == b"true" || value == b"1");
@@ -921,7 +1225,7 @@ impl Run {
                             b"i" => {
                                 let mut found_val = false;
                                 for attr in e.attributes().flatten() {
-                                    if attr.key.as_ref() == b"val" {
+                                    if attr.key.local_name().as_ref() == b"val" {
                                         found_val = true;
                                         let value = attr.value.as_ref();
                                         props.italic = Some(value == b"true" || value == b"1");
@@ -935,7 +1239,7 @@ impl Run {
                             b"strike" => {
                                 let mut found_val = false;
                                 for attr in e.attributes().flatten() {
-                                    if attr.key.as_ref() == b"val" {
+                                    if attr.key.local_name().as_ref() == b"val" {
                                         found_val = true;
                                         let value = attr.value.as_ref();
                                         props.strikethrough =
@@ -949,7 +1253,7 @@ impl Run {
                             },
                             b"vertAlign" => {
                                 for attr in e.attributes().flatten() {
-                                    if attr.key.as_ref() == b"val" {
+                                    if attr.key.local_name().as_ref() == b"val" {
                                         let value = attr.value.as_ref();
                                         props.vertical_position = match value {
                                             b"superscript" => Some(VerticalPosition::Superscript),
@@ -996,7 +1300,7 @@ impl Run {
                         in_r_pr = true;
                     } else if in_r_pr && name.as_ref() == b"vertAlign" {
                         for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"val" {
+                            if attr.key.local_name().as_ref() == b"val" {
                                 let value = attr.value.as_ref();
                                 match value {
                                     b"superscript" => {
@@ -1040,7 +1344,7 @@ impl Run {
                         in_r_pr = true;
                     } else if in_r_pr && name.as_ref() == b"rFonts" {
                         for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"ascii" {
+                            if attr.key.local_name().as_ref() == b"ascii" {
                                 let value = attr
                                     .decode_and_unescape_value(reader.decoder())
                                     .unwrap_or(Cow::Borrowed(""));
@@ -1081,7 +1385,7 @@ impl Run {
                         in_r_pr = true;
                     } else if in_r_pr && name.as_ref() == b"sz" {
                         for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"val"
+                            if attr.key.local_name().as_ref() == b"val"
                                 && let Ok(value) = std::str::from_utf8(&attr.value)
                                 && let Ok(size) = value.parse::<u32>()
                             {
@@ -1104,6 +1408,246 @@ impl Run {
         Ok(None)
     }
 
+    /// Get the text color of this run as written (`w:color`).
+    ///
+    /// A theme color reference (`w:themeColor`, with `w:themeTint` or
+    /// `w:themeShade`) takes precedence over the RGB value Word stores next
+    /// to it. Returns None if the color is inherited.
+    pub fn color_spec(&self) -> Result<Option<ColorSpec>> {
+        let mut reader = Reader::from_reader(self.xml_bytes());
+        reader.config_mut().trim_text(true);
+
+        let mut in_r_pr = false;
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    let name = e.local_name();
+                    if name.as_ref() == b"rPr" {
+                        in_r_pr = true;
+                    } else if in_r_pr && name.as_ref() == b"color" {
+                        return Ok(parse_color(&e));
+                    }
+                },
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"rPr" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the text color of this run, resolving theme colors against
+    /// `scheme` (usually [`Theme::colors`](crate::ooxml::docx::Theme::colors)).
+    ///
+    /// Returns None if the color is inherited or automatic.
+    pub fn color(&self, scheme: &ColorScheme) -> Result<Option<RGBColor>> {
+        Ok(self.color_spec()?.and_then(|spec| spec.resolve(scheme)))
+    }
+
+    /// Get the character style ID of this run (`w:rStyle`).
+    pub fn style_id(&self) -> Result<Option<String>> {
+        let mut reader = Reader::from_reader(self.xml_bytes());
+        reader.config_mut().trim_text(true);
+
+        let mut in_r_pr = false;
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    let name = e.local_name();
+                    if name.as_ref() == b"rPr" {
+                        in_r_pr = true;
+                    } else if in_r_pr && name.as_ref() == b"rStyle" {
+                        for attr in e.attributes().flatten() {
+                            if attr.key.local_name().as_ref() == b"val" {
+                                let value = attr
+                                    .decode_and_unescape_value(reader.decoder())
+                                    .unwrap_or(Cow::Borrowed(""));
+                                return Ok(Some(value.to_string()));
+                            }
+                        }
+                    }
+                },
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"rPr" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the languages set directly on this run (`w:lang`).
+    fn direct_language(&self) -> Result<Language> {
+        let mut reader = Reader::from_reader(self.xml_bytes());
+        reader.config_mut().trim_text(true);
+
+        let mut in_r_pr = false;
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    let name = e.local_name();
+                    if name.as_ref() == b"rPr" {
+                        in_r_pr = true;
+                    } else if in_r_pr && name.as_ref() == b"lang" {
+                        return Ok(parse_lang(&e));
+                    }
+                },
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"rPr" => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(Language::default())
+    }
+
+    /// Get the declared languages of this run as BCP-47 tags.
+    ///
+    /// For runs of body paragraphs returned by
+    /// [`Document::paragraphs`](crate::ooxml::docx::Document::paragraphs) and
+    /// [`Document::elements`](crate::ooxml::docx::Document::elements), the
+    /// languages are resolved like [`Run::effective_format`]: the run's own
+    /// `w:lang`, then its character style, then the paragraph style, then the
+    /// document defaults. For other runs, only the languages set on the run
+    /// itself are returned.
+    pub fn language(&self) -> Result<Language> {
+        let direct = self.direct_language()?;
+        let Some(inherited) = &self.inherited_language else {
+            return Ok(direct);
+        };
+        let mut language = (*inherited.paragraph).clone();
+        if let Some(character) = self
+            .style_id()?
+            .and_then(|id| inherited.styles.character(&id).cloned())
+        {
+            language.apply(&character);
+        }
+        language.apply(&direct);
+        Ok(language)
+    }
+
+    /// Get the character formatting set directly on this run.
+    pub fn direct_format(&self) -> Result<CharacterFormat> {
+        Ok(CharacterFormat {
+            bold: self.bold()?,
+            italic: self.italic()?,
+            font_name: self.font_name()?,
+            font_size: self.font_size()?,
+            color: self.color_spec()?,
+            language: self.direct_language()?,
+        })
+    }
+
+    /// Get the character formatting of this run as Word displays it.
+    ///
+    /// Direct run formatting wins over the run's character style, which
+    /// wins over the style of `paragraph` (the paragraph containing this
+    /// run), which wins over the document defaults. Each style includes the
+    /// styles it is based on. See [`Styles::resolve_character_format`].
+    pub fn effective_format(
+        &self,
+        paragraph: &Paragraph,
+        styles: &mut Styles<'_>,
+    ) -> Result<CharacterFormat> {
+        let mut format = styles.resolve_character_format(
+            paragraph.style_id()?.as_deref(),
+            self.style_id()?.as_deref(),
+        )?;
+        format.apply(&self.direct_format()?);
+        Ok(format)
+    }
+
+    /// Check if this run is displayed bold, taking styles into account.
+    ///
+    /// See [`Run::effective_format`].
+    pub fn effective_bold(&self, paragraph: &Paragraph, styles: &mut Styles<'_>) -> Result<bool> {
+        Ok(self
+            .effective_format(paragraph, styles)?
+            .bold
+            .unwrap_or(false))
+    }
+
+    /// Check if this run is displayed italic, taking styles into account.
+    ///
+    /// See [`Run::effective_format`].
+    pub fn effective_italic(&self, paragraph: &Paragraph, styles: &mut Styles<'_>) -> Result<bool> {
+        Ok(self
+            .effective_format(paragraph, styles)?
+            .italic
+            .unwrap_or(false))
+    }
+
+    /// Get the displayed font size of this run in half-points, taking
+    /// styles into account.
+    ///
+    /// Word uses 10pt (20 half-points) when no level sets a size.
+    pub fn effective_font_size(
+        &self,
+        paragraph: &Paragraph,
+        styles: &mut Styles<'_>,
+    ) -> Result<u32> {
+        Ok(self
+            .effective_format(paragraph, styles)?
+            .font_size
+            .unwrap_or(20))
+    }
+
+    /// Get the displayed text color of this run, taking styles into account
+    /// and resolving theme colors against `scheme`.
+    ///
+    /// Returns None if the color is automatic.
+    pub fn effective_color(
+        &self,
+        paragraph: &Paragraph,
+        styles: &mut Styles<'_>,
+        scheme: &ColorScheme,
+    ) -> Result<Option<RGBColor>> {
+        Ok(self
+            .effective_format(paragraph, styles)?
+            .color
+            .and_then(|spec| spec.resolve(scheme)))
+    }
+
+    /// Check if this run is an equation (`<m:oMath>`) rather than a `<w:r>`.
+    #[inline]
+    pub fn is_math(&self) -> bool {
+        self.xml_bytes().starts_with(b"<m:oMath")
+    }
+
+    /// Check if this run is a display equation.
+    ///
+    /// Display equations are wrapped in `<m:oMathPara>` and set on a line of
+    /// their own; other equations are inline with the surrounding text.
+    #[inline]
+    pub fn is_display_math(&self) -> bool {
+        self.display_math
+    }
+
+    /// Parse the equation in this run into a formula tree.
+    ///
+    /// Returns `None` if the run holds no equation. The nodes are allocated
+    /// in `arena`, typically that of a [`Formula`](crate::formula::Formula).
+    #[cfg(feature = "formula")]
+    pub fn math<'arena>(
+        &self,
+        arena: &'arena bumpalo::Bump,
+    ) -> Result<Option<Vec<crate::formula::MathNode<'arena>>>> {
+        let Some(omml) = self.omml_formula()? else {
+            return Ok(None);
+        };
+        crate::formula::OmmlParser::new(arena)
+            .parse(&omml)
+            .map(Some)
+            .map_err(|e| OoxmlError::InvalidFormat(format!("Invalid equation: {}", e)))
+    }
+
     /// Check if this run contains an OMML formula.
     ///
     /// Returns the OMML XML content if this run contains a mathematical formula,
@@ -1336,7 +1880,7 @@ impl Run {
                     } else if in_r_pr && name.as_ref() == property_name {
                         // Check for w:val attribute
                         for attr in e.attributes().flatten() {
-                            if attr.key.as_ref() == b"val" {
+                            if attr.key.local_name().as_ref() == b"val" {
                                 let value = attr.value.as_ref();
                                 return Ok(Some(value == b"true" || value == b"1"));
                             }
@@ -1360,6 +1904,62 @@ impl Run {
     }
 }
 
+/// Parse a `<w:color>` element.
+///
+/// A theme color reference (`w:themeColor`, with `w:themeTint` or
+/// `w:themeShade`) takes precedence over the RGB value Word stores next to it.
+pub(crate) fn parse_color(e: &BytesStart<'_>) -> Option<ColorSpec> {
+    let (mut val, mut theme_color) = (None, None);
+    let (mut tint, mut shade) = (None, None);
+    for attr in e.attributes().flatten() {
+        let value = String::from_utf8_lossy(&attr.value);
+        match attr.key.local_name().as_ref() {
+            b"val" => val = Some(value.into_owned()),
+            b"themeColor" => theme_color = ThemeColorSlot::from_word_name(&value),
+            b"themeTint" => tint = u8::from_str_radix(&value, 16).ok(),
+            b"themeShade" => shade = u8::from_str_radix(&value, 16).ok(),
+            _ => {},
+        }
+    }
+
+    if let Some(slot) = theme_color {
+        return Some(ColorSpec::Theme {
+            slot,
+            transforms: word_tint_shade(tint, shade),
+        });
+    }
+    match val.as_deref() {
+        Some("auto") => Some(ColorSpec::Auto),
+        Some(hex) => RGBColor::from_hex(hex).ok().map(ColorSpec::Rgb),
+        None => None,
+    }
+}
+
+/// Get the character standing in for a run content element other than `w:t`.
+///
+/// Tabs (`w:tab`, `w:ptab`) are `'\t'`, line breaks (`w:br`, `w:cr`) are
+/// `'\n'` and non-breaking hyphens are U+2011. Soft hyphens only mark an
+/// optional break, so they give no text. With `page_breaks`, page breaks are
+/// form feeds (`'\x0c'`).
+///
+/// Tab stops in `w:pPr` share the `w:tab` name; callers skip paragraph
+/// properties before asking.
+pub(crate) fn run_content_char(e: &BytesStart<'_>, page_breaks: bool) -> Option<char> {
+    match e.local_name().as_ref() {
+        b"tab" | b"ptab" => Some('\t'),
+        b"br" => {
+            let page = page_breaks
+                && e.attributes().flatten().any(|attr| {
+                    attr.key.local_name().as_ref() == b"type" && attr.value.as_ref() == b"page"
+                });
+            Some(if page { '\x0c' } else { '\n' })
+        },
+        b"cr" => Some('\n'),
+        b"noBreakHyphen" => Some('\u{2011}'),
+        _ => None,
+    }
+}
+
 /// Count runs in a paragraph XML (for pre-allocation).
 #[inline]
 fn count_runs(xml_bytes: &[u8]) -> usize {
@@ -1373,21 +1973,33 @@ fn count_runs(xml_bytes: &[u8]) -> usize {
         };
         let tag_start = i + tag_start;
 
-        if tag_start + 5 < len && &xml_bytes[tag_start..tag_start + 4] == b"<w:r" {
-            let c = xml_bytes[tag_start + 4];
-            if (c == b'>' || c == b' ' || c == b'/')
-                && let Some(end) = find_run_end(&xml_bytes[tag_start..])
-            {
-                count += 1;
-                i = tag_start + end;
-                continue;
-            }
+        if let Some(end) = run_end(&xml_bytes[tag_start..]) {
+            count += 1;
+            i = tag_start + end;
+            continue;
         }
         i = tag_start + 1;
     }
     count
 }
 
+/// Find the end of the run starting at the beginning of `xml`, if any.
+///
+/// Runs are `<w:r>` elements and `<m:oMath>` equations.
+/// Returns byte offset AFTER the closing tag.
+#[inline]
+fn run_end(xml: &[u8]) -> Option<usize> {
+    let next_char = *xml.get(4)?;
+    if xml.starts_with(b"<w:r") && matches!(next_char, b'>' | b' ' | b'/') {
+        return find_run_end(xml);
+    }
+    if xml.starts_with(b"<m:oMath") && matches!(xml.get(8)?, b'>' | b' ') {
+        const CLOSING: &[u8] = b"</m:oMath>";
+        return memchr::memmem::find(xml, CLOSING).map(|pos| pos + CLOSING.len());
+    }
+    None
+}
+
 /// Find the end of a `<w:r>` element.
 /// Returns byte offset AFTER the closing `</w:r>`.
 #[inline]
@@ -1445,6 +2057,17 @@ mod tests {
         assert_eq!(text, "Hello, World!");
     }
 
+    #[test]
+    fn test_run_text_with_page_breaks() {
+        let xml = br#"<w:r xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:t>one</w:t><w:br/><w:t>two</w:t><w:br w:type="page"/><w:t>three</w:t>
+        </w:r>"#;
+
+        let run = Run::new(xml.to_vec());
+        assert_eq!(run.text().unwrap(), "one\ntwo\nthree");
+        assert_eq!(run.text_with_page_breaks().unwrap(), "one\ntwo\x0cthree");
+    }
+
     #[test]
     fn test_run_bold() {
         let xml = br#"<w:r xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
@@ -1466,4 +2089,67 @@ mod tests {
         let run = Run::new(xml.to_vec());
         assert!(run.italic().unwrap().unwrap_or(false));
     }
+
+    #[test]
+    fn test_run_direct_format() {
+        let xml = br#"<w:r xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:rPr><w:rStyle w:val="Strong"/><w:rFonts w:ascii="Arial" w:hAnsi="Arial"/><w:b w:val="0"/><w:sz w:val="28"/></w:rPr>
+            <w:t>Text</w:t>
+        </w:r>"#;
+
+        let run = Run::new(xml.to_vec());
+        assert_eq!(run.style_id().unwrap().as_deref(), Some("Strong"));
+        let format = run.direct_format().unwrap();
+        assert_eq!(format.bold, Some(false));
+        assert_eq!(format.italic, None);
+        assert_eq!(format.font_name.as_deref(), Some("Arial"));
+        assert_eq!(format.font_size, Some(28));
+    }
+
+    #[test]
+    fn test_run_color() {
+        let scheme = ColorScheme::default();
+
+        let xml = br#"<w:r><w:rPr><w:color w:val="2F5496" w:themeColor="accent1" w:themeShade="BF"/></w:rPr><w:t>Theme</w:t></w:r>"#;
+        let run = Run::new(xml.to_vec());
+        assert!(matches!(
+            run.color_spec().unwrap(),
+            Some(ColorSpec::Theme {
+                slot: ThemeColorSlot::Accent1,
+                ..
+            })
+        ));
+        let color = run.color(&scheme).unwrap().unwrap();
+        assert!(color.r.abs_diff(0x2F) <= 1 && color.b.abs_diff(0x96) <= 1);
+
+        let run = Run::new(br#"<w:r><w:rPr><w:color w:val="FF0000"/></w:rPr></w:r>"#.to_vec());
+        assert_eq!(run.color(&scheme).unwrap(), Some(RGBColor::new(0xFF, 0, 0)));
+
+        let run = Run::new(br#"<w:r><w:rPr><w:color w:val="auto"/></w:rPr></w:r>"#.to_vec());
+        assert_eq!(run.color_spec().unwrap(), Some(ColorSpec::Auto));
+        assert_eq!(run.color(&scheme).unwrap(), None);
+
+        let run = Run::new(br#"<w:r><w:t>Plain</w:t></w:r>"#.to_vec());
+        assert_eq!(run.color_spec().unwrap(), None);
+    }
+
+    #[test]
+    fn test_paragraph_math_runs() {
+        let xml = br#"<w:p><w:r><w:t xml:space="preserve">Let </w:t></w:r><m:oMath><m:r><m:t>x</m:t></m:r></m:oMath><w:r><w:t xml:space="preserve"> hold:</w:t></w:r><m:oMathPara><m:oMath><m:r><m:t>y</m:t></m:r></m:oMath></m:oMathPara></w:p>"#;
+        let para = Paragraph::new(xml.to_vec());
+        assert!(para.has_math());
+
+        let runs = para.runs().unwrap();
+        let kinds: Vec<_> = runs
+            .iter()
+            .map(|run| (run.is_math(), run.is_display_math()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [(false, false), (true, false), (false, false), (true, true)]
+        );
+        assert_eq!(runs[0].text().unwrap(), "Let ");
+        assert_eq!(runs[2].text().unwrap(), " hold:");
+        assert_eq!(para.omml_formulas().unwrap().len(), 2);
+    }
 }
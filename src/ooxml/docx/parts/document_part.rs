@@ -1,11 +1,18 @@
 /// DocumentPart - the main document.xml part of a Word document.
-use crate::ooxml::docx::paragraph::Paragraph;
+use crate::common::trace;
+use crate::common::walk::{TextEvent, visit, visit_lines};
+use crate::document::RevisionMode;
+use crate::ooxml::docx::content_control::push_general_ref;
+use crate::ooxml::docx::paragraph::{Paragraph, run_content_char};
+use crate::ooxml::docx::revision::{Revision, parse_revisions, revision_text};
 use crate::ooxml::docx::table::Table;
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::part::Part;
+use crate::ooxml::pptx::smartart::data_rel_id;
 use quick_xml::Reader;
 use quick_xml::events::Event;
 use smallvec::SmallVec;
+use std::ops::{ControlFlow, Range};
 use std::sync::Arc;
 
 /// The main document part of a Word document.
@@ -25,6 +32,9 @@ impl<'a> DocumentPart<'a> {
     ///
     /// * `part` - The part containing the document.xml content
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        // Read deferred content now, so that a package file that can no
+        // longer be read fails here instead of yielding empty XML
+        part.try_blob()?;
         Ok(Self { part })
     }
 
@@ -40,37 +50,103 @@ impl<'a> DocumentPart<'a> {
         self.part.blob()
     }
 
+    /// Error for malformed XML at byte `position` of the document.
+    fn malformed_xml(&self, position: u64, source: quick_xml::Error) -> OoxmlError {
+        OoxmlError::malformed_xml(
+            self.part.partname().as_str(),
+            self.xml_bytes(),
+            position,
+            source,
+        )
+    }
+
     /// Extract all paragraph text from the document.
     ///
     /// This performs a quick extraction of all text content by finding
-    /// `<w:t>` elements in the XML.
+    /// `<w:t>` elements in the XML. Paragraphs are separated by newlines,
+    /// and tabs, breaks and hyphens are converted as by
+    /// [`Run::text`](crate::ooxml::docx::Run::text).
     ///
     /// # Performance
     ///
     /// Uses `quick-xml` for efficient streaming XML parsing with pre-allocated
     /// buffer and unsafe string conversion for optimal performance.
     pub fn extract_text(&self) -> Result<String> {
+        self.extract_text_with_diagrams(&|_| Ok(None))
+    }
+
+    /// Extract all paragraph text from the document, including SmartArt text.
+    ///
+    /// `diagram_text` maps the data part relationship ID (`r:dm`) of each
+    /// SmartArt graphic to its text, which is placed on lines of its own
+    /// where the graphic is anchored.
+    pub fn extract_text_with_diagrams(
+        &self,
+        diagram_text: &dyn Fn(&str) -> Result<Option<String>>,
+    ) -> Result<String> {
         let mut reader = Reader::from_reader(self.xml_bytes());
-        reader.config_mut().trim_text(true);
+        reader.config_mut().trim_text(false); // `xml:space="preserve"` text keeps its spaces
 
         // Pre-allocate with estimated capacity to reduce reallocations
         let estimated_capacity = self.xml_bytes().len() / 8; // Rough estimate for text content
         let mut result = String::with_capacity(estimated_capacity);
         let mut in_text_element = false;
+        let mut paragraphs = 0usize;
+        // Whether the last thing written was a SmartArt block, which ends its own line
+        let mut after_diagram = false;
 
         // Use read_event() for zero-copy parsing from slice
         loop {
             match reader.read_event() {
-                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
-                    // Check if this is a w:t element
-                    if e.local_name().as_ref() == b"t" {
-                        in_text_element = true;
+                Ok(Event::Start(e))
+                    if matches!(e.local_name().as_ref(), b"txbxContent" | b"pPr") =>
+                {
+                    // Text box content is reported by `Paragraph::text_boxes`, and
+                    // tab stops (`w:tabs/w:tab`) are not text
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| self.malformed_xml(reader.error_position(), e))?;
+                },
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"p" => {
+                    // Self-closing paragraphs are skipped, as by `paragraphs`
+                    if paragraphs > 0 && !after_diagram {
+                        result.push('\n');
                     }
+                    paragraphs += 1;
+                    after_diagram = false;
+                },
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    // Check if this is a w:t element
+                    b"t" => in_text_element = true,
+                    b"relIds" => {
+                        if let Some(rel_id) = data_rel_id(&e)
+                            && let Some(diagram) = diagram_text(&rel_id)?
+                            && !diagram.is_empty()
+                        {
+                            if !result.is_empty() && !result.ends_with('\n') {
+                                result.push('\n');
+                            }
+                            result.push_str(&diagram);
+                            result.push('\n');
+                            after_diagram = true;
+                        }
+                    },
+                    _ => {
+                        if let Some(ch) = run_content_char(&e, false) {
+                            result.push(ch);
+                            after_diagram = false;
+                        }
+                    },
                 },
                 Ok(Event::Text(e)) if in_text_element => {
                     // Extract text content - use unsafe conversion for better performance
                     let text = unsafe { std::str::from_utf8_unchecked(e.as_ref()) };
                     result.push_str(text);
+                    after_diagram = false;
+                },
+                Ok(Event::GeneralRef(e)) if in_text_element => {
+                    push_general_ref(&mut result, &e)?;
+                    after_diagram = false;
                 },
                 Ok(Event::End(e)) => {
                     if e.local_name().as_ref() == b"t" {
@@ -78,7 +154,9 @@ impl<'a> DocumentPart<'a> {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                Err(e) => {
+                    return Err(self.malformed_xml(reader.error_position(), e));
+                },
                 _ => {},
             }
         }
@@ -86,6 +164,95 @@ impl<'a> DocumentPart<'a> {
         Ok(result)
     }
 
+    /// Report the body text to `visitor` while parsing, stopping as soon as
+    /// it returns [`ControlFlow::Break`].
+    ///
+    /// Reports the same text as
+    /// [`extract_text_with_diagrams`](Self::extract_text_with_diagrams),
+    /// with table structure; see [`TextEvent`].
+    pub fn walk_text(
+        &self,
+        diagram_text: &dyn Fn(&str) -> Result<Option<String>>,
+        visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        let mut reader = Reader::from_reader(self.xml_bytes());
+        reader.config_mut().trim_text(false);
+
+        let mut in_text_element = false;
+        let mut chars = String::new();
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e))
+                    if matches!(e.local_name().as_ref(), b"txbxContent" | b"pPr") =>
+                {
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| self.malformed_xml(reader.error_position(), e))?;
+                },
+                Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                    b"p" => visit!(visitor, TextEvent::ParagraphStart),
+                    b"t" => in_text_element = true,
+                    b"tbl" => visit!(visitor, TextEvent::TableStart),
+                    b"tr" => visit!(visitor, TextEvent::TableRowStart),
+                    b"tc" => visit!(visitor, TextEvent::TableCellStart),
+                    _ => {
+                        if let Some(ch) = run_content_char(&e, false) {
+                            visit!(visitor, TextEvent::Text(ch.encode_utf8(&mut [0; 4])));
+                        }
+                    },
+                },
+                Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    b"relIds" => {
+                        if let Some(rel_id) = data_rel_id(&e)
+                            && let Some(diagram) = diagram_text(&rel_id)?
+                            && visit_lines(&diagram, visitor).is_break()
+                        {
+                            return Ok(ControlFlow::Break(()));
+                        }
+                    },
+                    _ => {
+                        if let Some(ch) = run_content_char(&e, false) {
+                            visit!(visitor, TextEvent::Text(ch.encode_utf8(&mut [0; 4])));
+                        }
+                    },
+                },
+                Ok(Event::Text(e)) if in_text_element => {
+                    let text = std::str::from_utf8(e.as_ref())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    visit!(visitor, TextEvent::Text(text));
+                },
+                Ok(Event::GeneralRef(e)) if in_text_element => {
+                    chars.clear();
+                    push_general_ref(&mut chars, &e)?;
+                    visit!(visitor, TextEvent::Text(&chars));
+                },
+                Ok(Event::End(e)) => match e.local_name().as_ref() {
+                    b"t" => in_text_element = false,
+                    b"tbl" => visit!(visitor, TextEvent::TableEnd),
+                    _ => {},
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(self.malformed_xml(reader.error_position(), e)),
+                _ => {},
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Extract text with tracked changes materialized according to `mode`.
+    ///
+    /// Paragraphs are separated by newlines.
+    pub fn extract_text_with_revisions(&self, mode: RevisionMode) -> Result<String> {
+        revision_text(self.xml_bytes(), mode, true)
+    }
+
+    /// Extract every tracked change (revision) in the document body.
+    pub fn extract_revisions(&self) -> Result<SmallVec<[Revision; 4]>> {
+        parse_revisions(self.xml_bytes())
+    }
+
     /// Count the number of paragraphs in the document.
     ///
     /// Counts `<w:p>` elements in the document body.
@@ -97,13 +264,21 @@ impl<'a> DocumentPart<'a> {
 
         loop {
             match reader.read_event() {
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"txbxContent" => {
+                    // Paragraphs of text boxes are not body paragraphs
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| self.malformed_xml(reader.error_position(), e))?;
+                },
                 Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                     if e.local_name().as_ref() == b"p" {
                         count += 1;
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                Err(e) => {
+                    return Err(self.malformed_xml(reader.error_position(), e));
+                },
                 _ => {},
             }
         }
@@ -128,7 +303,9 @@ impl<'a> DocumentPart<'a> {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                Err(e) => {
+                    return Err(self.malformed_xml(reader.error_position(), e));
+                },
                 _ => {},
             }
         }
@@ -147,58 +324,62 @@ impl<'a> DocumentPart<'a> {
     /// allocations via pre-sized reserves.
     pub fn paragraphs(&self) -> Result<SmallVec<[Paragraph; 32]>> {
         let xml_bytes = self.xml_bytes();
-        let mut reader = Reader::from_reader(xml_bytes);
-        reader.config_mut().trim_text(true);
+        let _span = trace::debug_span!(
+            "parse_part",
+            part = %self.part.partname(),
+            size = xml_bytes.len(),
+            content = "paragraphs"
+        )
+        .entered();
 
         // Estimate paragraph count
         let estimated = (xml_bytes.len() / 400).max(8);
         let mut paragraphs = SmallVec::with_capacity(estimated);
-        let mut current_para_xml = Vec::with_capacity(4096);
-        let mut in_para = false;
-        let mut depth = 0u32;
+        parse_paragraphs(xml_bytes, |para| paragraphs.push(para))
+            .map_err(|(position, e)| self.malformed_xml(position, e))?;
 
-        // Use read_event() for zero-copy parsing from slice
-        loop {
-            match reader.read_event() {
-                Ok(Event::Start(e)) => {
-                    if e.local_name().as_ref() == b"p" && !in_para {
-                        in_para = true;
-                        depth = 1;
-                        current_para_xml.clear();
-                        write_start_tag(&mut current_para_xml, &e);
-                    } else if in_para {
-                        depth += 1;
-                        write_start_tag_dynamic(&mut current_para_xml, &e);
-                    }
-                },
-                Ok(Event::End(e)) => {
-                    if in_para {
-                        write_end_tag(&mut current_para_xml, e.name().as_ref());
-                        depth -= 1;
-                        if depth == 0 && e.local_name().as_ref() == b"p" {
-                            // Clone bytes and clear buffer (preserves capacity for next element)
-                            let para_xml = current_para_xml.clone();
-                            current_para_xml.clear();
-                            paragraphs.push(Paragraph::new(para_xml));
-                            in_para = false;
-                        }
-                    }
-                },
-                Ok(Event::Text(e)) if in_para => {
-                    current_para_xml.extend_from_slice(e.as_ref());
-                },
-                Ok(Event::Empty(e)) if in_para => {
-                    write_empty_tag(&mut current_para_xml, &e);
-                },
-                Ok(Event::Eof) => break,
-                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
-                _ => {},
-            }
+        Ok(paragraphs)
+    }
+
+    /// Get the paragraphs with indices in `range`.
+    ///
+    /// Indices match [`paragraphs`](Self::paragraphs); indices past the last
+    /// paragraph are ignored. Paragraph boundaries are found with a byte scan,
+    /// so only the requested paragraphs are parsed.
+    pub fn paragraphs_range(&self, range: Range<usize>) -> Result<Vec<Paragraph>> {
+        let xml_bytes = self.xml_bytes();
+        let spans = paragraph_spans(xml_bytes, range.end);
+        let spans = spans.get(range.start..).unwrap_or_default();
+
+        let mut paragraphs = Vec::with_capacity(spans.len());
+        for span in spans {
+            parse_paragraphs(&xml_bytes[span.clone()], |para| paragraphs.push(para))
+                .map_err(|(position, e)| self.malformed_xml(span.start as u64 + position, e))?;
         }
 
         Ok(paragraphs)
     }
 
+    /// Byte ranges of the first `limit` paragraphs in the document XML.
+    ///
+    /// The ranges cover the paragraphs yielded by
+    /// [`paragraphs`](Self::paragraphs), in the same order.
+    pub(crate) fn paragraph_spans(&self, limit: usize) -> Vec<Range<usize>> {
+        paragraph_spans(self.xml_bytes(), limit)
+    }
+
+    /// Wrap a byte range of the document XML as a paragraph without copying.
+    ///
+    /// The paragraph keeps the original markup, which is enough to read its
+    /// properties but is not normalized like [`paragraphs`](Self::paragraphs).
+    pub(crate) fn raw_paragraph(&self, span: Range<usize>) -> Paragraph {
+        Paragraph::from_arc_range(
+            self.get_xml_arc(),
+            span.start as u32,
+            (span.end - span.start) as u32,
+        )
+    }
+
     /// Get all tables in the document.
     ///
     /// Extracts all `<w:tbl>` elements from the document body.
@@ -209,8 +390,15 @@ impl<'a> DocumentPart<'a> {
     /// Optimized to minimize allocations via pre-sized reserves.
     pub fn tables(&self) -> Result<SmallVec<[Table; 8]>> {
         let xml_bytes = self.xml_bytes();
+        let _span = trace::debug_span!(
+            "parse_part",
+            part = %self.part.partname(),
+            size = xml_bytes.len(),
+            content = "tables"
+        )
+        .entered();
         let mut reader = Reader::from_reader(xml_bytes);
-        reader.config_mut().trim_text(true);
+        reader.config_mut().trim_text(false); // `xml:space="preserve"` text keeps its spaces
 
         let mut tables = SmallVec::new();
         let mut current_table_xml = Vec::with_capacity(8192);
@@ -247,11 +435,16 @@ impl<'a> DocumentPart<'a> {
                 Ok(Event::Text(e)) if in_table => {
                     current_table_xml.extend_from_slice(e.as_ref());
                 },
+                Ok(Event::GeneralRef(e)) if in_table => {
+                    write_general_ref(&mut current_table_xml, &e);
+                },
                 Ok(Event::Empty(e)) if in_table => {
                     write_empty_tag(&mut current_table_xml, &e);
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                Err(e) => {
+                    return Err(self.malformed_xml(reader.error_position(), e));
+                },
                 _ => {},
             }
         }
@@ -442,6 +635,14 @@ fn write_empty_tag(out: &mut Vec<u8>, e: &quick_xml::events::BytesStart<'_>) {
     }
 }
 
+/// Write an entity or character reference "&name;" back out.
+#[inline(always)]
+fn write_general_ref(out: &mut Vec<u8>, e: &quick_xml::events::BytesRef<'_>) {
+    out.push(b'&');
+    out.extend_from_slice(e.as_ref());
+    out.push(b';');
+}
+
 /// Write a closing tag "</name>" efficiently.
 #[inline(always)]
 fn write_end_tag(out: &mut Vec<u8>, name: &[u8]) {
@@ -458,6 +659,102 @@ fn write_end_tag(out: &mut Vec<u8>, name: &[u8]) {
     }
 }
 
+/// Parse every `<w:p>` element that is not nested in another paragraph.
+///
+/// Each paragraph is re-serialized into its own buffer and handed to `push`.
+/// Errors carry the byte position in `xml_bytes` where parsing failed.
+pub(crate) fn parse_paragraphs(
+    xml_bytes: &[u8],
+    mut push: impl FnMut(Paragraph),
+) -> std::result::Result<(), (u64, quick_xml::Error)> {
+    let mut reader = Reader::from_reader(xml_bytes);
+    reader.config_mut().trim_text(false); // `xml:space="preserve"` text keeps its spaces
+
+    let mut current_para_xml = Vec::with_capacity(4096);
+    let mut in_para = false;
+    let mut depth = 0u32;
+
+    // Use read_event() for zero-copy parsing from slice
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                if e.local_name().as_ref() == b"p" && !in_para {
+                    in_para = true;
+                    depth = 1;
+                    current_para_xml.clear();
+                    write_start_tag(&mut current_para_xml, &e);
+                } else if in_para {
+                    depth += 1;
+                    write_start_tag_dynamic(&mut current_para_xml, &e);
+                }
+            },
+            Ok(Event::End(e)) => {
+                if in_para {
+                    write_end_tag(&mut current_para_xml, e.name().as_ref());
+                    depth -= 1;
+                    if depth == 0 && e.local_name().as_ref() == b"p" {
+                        // Clone bytes and clear buffer (preserves capacity for next element)
+                        let para_xml = current_para_xml.clone();
+                        current_para_xml.clear();
+                        push(Paragraph::new(para_xml));
+                        in_para = false;
+                    }
+                }
+            },
+            Ok(Event::Text(e)) if in_para => {
+                current_para_xml.extend_from_slice(e.as_ref());
+            },
+            Ok(Event::GeneralRef(e)) if in_para => {
+                write_general_ref(&mut current_para_xml, &e);
+            },
+            Ok(Event::Empty(e)) if in_para => {
+                write_empty_tag(&mut current_para_xml, &e);
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err((reader.error_position(), e)),
+            _ => {},
+        }
+    }
+
+    Ok(())
+}
+
+/// Find the byte ranges of the first `limit` paragraphs.
+///
+/// Matches the paragraphs [`parse_paragraphs`] yields: non-empty `<w:p>`
+/// elements, including those inside tables, but not paragraphs nested in
+/// another paragraph (such as text box content).
+fn paragraph_spans(xml_bytes: &[u8], limit: usize) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut i = 0usize;
+    let len = xml_bytes.len();
+
+    while i < len && spans.len() < limit {
+        let Some(tag_start) = memchr::memchr(b'<', &xml_bytes[i..]) else {
+            break;
+        };
+        let tag_start = i + tag_start;
+
+        if tag_start + 5 < len && &xml_bytes[tag_start..tag_start + 4] == b"<w:p" {
+            let next_char = xml_bytes[tag_start + 4];
+            if (next_char == b'>' || next_char == b' ')
+                && let Some(end) = find_paragraph_end(&xml_bytes[tag_start..])
+            {
+                // Self-closing paragraphs are skipped, as in `parse_paragraphs`
+                if xml_bytes[tag_start + end - 2] != b'/' {
+                    spans.push(tag_start..tag_start + end);
+                }
+                i = tag_start + end;
+                continue;
+            }
+        }
+
+        i = tag_start + 1;
+    }
+
+    spans
+}
+
 /// Count the number of top-level paragraphs and tables in the XML.
 /// This is a fast pre-scan to determine exact allocation size.
 #[inline]
@@ -591,5 +888,32 @@ fn find_table_end(xml: &[u8]) -> Option<usize> {
 
 #[cfg(test)]
 mod tests {
-    // Tests will be added as we have a way to construct test XmlParts
+    use super::*;
+
+    const BODY: &[u8] = br#"<w:body><w:p><w:r><w:t>one</w:t></w:r></w:p><w:p w:rsidR="1"/><w:tbl><w:tr><w:tc><w:p><w:pPr><w:pStyle w:val="A"/></w:pPr><w:r><w:t>cell</w:t></w:r></w:p></w:tc></w:tr></w:tbl><w:p><w:r><w:txbxContent><w:p><w:r><w:t>inner</w:t></w:r></w:p></w:txbxContent></w:r></w:p><w:p><w:r><w:t>last</w:t></w:r></w:p><w:sectPr/></w:body>"#;
+
+    fn texts(paragraphs: &[Paragraph]) -> Vec<String> {
+        paragraphs.iter().map(|p| p.text().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_paragraph_spans_match_parse() {
+        let mut all = Vec::new();
+        parse_paragraphs(BODY, |p| all.push(p)).unwrap();
+        // Text box content is not part of the anchoring paragraph's text
+        assert_eq!(texts(&all), ["one", "cell", "", "last"]);
+        assert_eq!(all[2].text_boxes().unwrap()[0].text().unwrap(), "inner");
+
+        let spans = paragraph_spans(BODY, usize::MAX);
+        assert_eq!(spans.len(), all.len());
+        for (span, para) in spans.iter().zip(&all) {
+            let mut parsed = Vec::new();
+            parse_paragraphs(&BODY[span.clone()], |p| parsed.push(p)).unwrap();
+            assert_eq!(parsed.len(), 1);
+            assert_eq!(parsed[0].text().unwrap(), para.text().unwrap());
+        }
+
+        assert_eq!(paragraph_spans(BODY, 2), spans[..2]);
+        assert!(paragraph_spans(BODY, 0).is_empty());
+    }
 }
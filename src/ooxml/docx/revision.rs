@@ -33,6 +33,8 @@
 /// }
 /// # Ok::<(), Box<dyn std::error::Error>>(())
 /// ```
+use crate::document::RevisionMode;
+use crate::ooxml::docx::paragraph::run_content_char;
 use crate::ooxml::error::{OoxmlError, Result};
 use quick_xml::Reader;
 use quick_xml::events::Event;
@@ -214,32 +216,22 @@ pub(crate) fn parse_revisions(xml_bytes: &[u8]) -> Result<SmallVec<[Revision; 4]
     // Use SmallVec for efficient storage of typically small revision collections
     let mut revisions = SmallVec::new();
 
-    // State tracking for parsing
-    let mut in_revision = false;
+    // Revisions can nest (e.g. a formatting change inside an insertion),
+    // so keep the open ones on a stack and attribute text to the innermost
+    let mut open: SmallVec<[Revision; 2]> = SmallVec::new();
     let mut in_revision_text = false;
-    let mut current_revision: Option<Revision> = None;
 
     loop {
         match reader.read_event() {
-            Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+            Ok(event @ (Event::Start(_) | Event::Empty(_))) => {
+                let (Event::Start(e) | Event::Empty(e)) = &event else {
+                    unreachable!()
+                };
+                let is_empty = matches!(event, Event::Empty(_));
                 let local_name_ref = e.local_name();
                 let local_name = local_name_ref.as_ref();
 
-                // Check for revision elements
-                let revision_type = match local_name {
-                    b"ins" => Some(RevisionType::Insert),
-                    b"del" => Some(RevisionType::Delete),
-                    b"moveFrom" => Some(RevisionType::MoveFrom),
-                    b"moveTo" => Some(RevisionType::MoveTo),
-                    b"rPrChange" => Some(RevisionType::FormatChange),
-                    b"tblIns" => Some(RevisionType::TableInsert),
-                    b"tblDel" => Some(RevisionType::TableDelete),
-                    _ => None,
-                };
-
-                if let Some(rev_type) = revision_type {
-                    in_revision = true;
-
+                if let Some(rev_type) = revision_type_of(local_name) {
                     // Parse revision attributes
                     let mut author = String::new();
                     let mut date = None;
@@ -266,20 +258,23 @@ pub(crate) fn parse_revisions(xml_bytes: &[u8]) -> Result<SmallVec<[Revision; 4]
                         }
                     }
 
-                    current_revision = Some(Revision::new(rev_type, author, date, id));
-                } else if in_revision {
+                    let revision = Revision::new(rev_type, author, date, id);
+                    if is_empty {
+                        // Paragraph mark revisions carry no content
+                        revisions.push(revision);
+                    } else {
+                        open.push(revision);
+                    }
+                } else if !open.is_empty() && !is_empty {
                     // Check for text elements within revision
-                    match local_name {
-                        b"t" | b"delText" => {
-                            in_revision_text = true;
-                        },
-                        _ => {},
+                    if matches!(local_name, b"t" | b"delText") {
+                        in_revision_text = true;
                     }
                 }
             },
             Ok(Event::Text(e)) if in_revision_text => {
                 // Extract text content from revision
-                if let Some(ref mut rev) = current_revision
+                if let Some(rev) = open.last_mut()
                     && let Ok(text) = std::str::from_utf8(e.as_ref())
                 {
                     rev.append_text(text);
@@ -289,20 +284,13 @@ pub(crate) fn parse_revisions(xml_bytes: &[u8]) -> Result<SmallVec<[Revision; 4]
                 let local_name_ref = e.local_name();
                 let local_name = local_name_ref.as_ref();
 
-                match local_name {
-                    b"ins" | b"del" | b"moveFrom" | b"moveTo" | b"rPrChange" | b"tblIns"
-                    | b"tblDel" => {
-                        // Finished parsing a revision
-                        in_revision = false;
-
-                        if let Some(revision) = current_revision.take() {
-                            revisions.push(revision);
-                        }
-                    },
-                    b"t" | b"delText" => {
-                        in_revision_text = false;
-                    },
-                    _ => {},
+                if revision_type_of(local_name).is_some() {
+                    // Finished parsing a revision
+                    if let Some(revision) = open.pop() {
+                        revisions.push(revision);
+                    }
+                } else if matches!(local_name, b"t" | b"delText") {
+                    in_revision_text = false;
                 }
             },
             Ok(Event::Eof) => break,
@@ -314,6 +302,191 @@ pub(crate) fn parse_revisions(xml_bytes: &[u8]) -> Result<SmallVec<[Revision; 4]
     Ok(revisions)
 }
 
+/// Map a revision element name to its revision type.
+#[inline]
+fn revision_type_of(local_name: &[u8]) -> Option<RevisionType> {
+    match local_name {
+        b"ins" => Some(RevisionType::Insert),
+        b"del" => Some(RevisionType::Delete),
+        b"moveFrom" => Some(RevisionType::MoveFrom),
+        b"moveTo" => Some(RevisionType::MoveTo),
+        b"rPrChange" => Some(RevisionType::FormatChange),
+        b"tblIns" => Some(RevisionType::TableInsert),
+        b"tblDel" => Some(RevisionType::TableDelete),
+        _ => None,
+    }
+}
+
+/// Extract text with tracked changes accepted, rejected or marked up.
+///
+/// Text inside `w:ins`/`w:moveTo` is treated as inserted and text inside
+/// `w:del`/`w:moveFrom` as deleted. In [`RevisionMode::ShowMarkup`] both are
+/// kept and wrapped in CriticMarkup (`{++…++}` and `{--…--}`).
+///
+/// When `separate_paragraphs` is set, paragraphs are separated by newlines;
+/// otherwise text is concatenated like [`Paragraph::text`](super::Paragraph::text).
+pub(crate) fn revision_text(
+    xml_bytes: &[u8],
+    mode: RevisionMode,
+    separate_paragraphs: bool,
+) -> Result<String> {
+    let mut reader = Reader::from_reader(xml_bytes);
+    reader.config_mut().trim_text(false);
+
+    let mut result = String::with_capacity(xml_bytes.len() / 8);
+    let mut inserted = 0usize;
+    let mut deleted = 0usize;
+    let mut in_text = false;
+    let mut paragraphs = 0usize;
+    // Markup currently open in ShowMarkup mode: Some(true) for insertions
+    let mut open_markup: Option<bool> = None;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"ins" | b"moveTo" => inserted += 1,
+                b"del" | b"moveFrom" => deleted += 1,
+                b"t" | b"delText" => in_text = true,
+                b"txbxContent" => {
+                    // Text box content is reported by `Paragraph::text_boxes`
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                },
+                b"pPr" => {
+                    // Tab stops (`w:tabs/w:tab`) are not text
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                },
+                b"p" if separate_paragraphs => {
+                    close_markup(&mut result, &mut open_markup);
+                    if paragraphs > 0 {
+                        result.push('\n');
+                    }
+                    paragraphs += 1;
+                },
+                _ => {
+                    if let Some(ch) = run_content_char(&e, false) {
+                        push_revision_text(
+                            &mut result,
+                            &mut open_markup,
+                            mode,
+                            inserted,
+                            deleted,
+                            ch.encode_utf8(&mut [0; 4]),
+                        );
+                    }
+                },
+            },
+            Ok(Event::Empty(e)) => {
+                if let Some(ch) = run_content_char(&e, false) {
+                    push_revision_text(
+                        &mut result,
+                        &mut open_markup,
+                        mode,
+                        inserted,
+                        deleted,
+                        ch.encode_utf8(&mut [0; 4]),
+                    );
+                }
+            },
+            Ok(Event::Text(e)) if in_text => {
+                let text = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                push_revision_text(
+                    &mut result,
+                    &mut open_markup,
+                    mode,
+                    inserted,
+                    deleted,
+                    &text,
+                );
+            },
+            Ok(Event::GeneralRef(e)) if in_text => {
+                // Entity references are reported apart from the surrounding text
+                let mut text = String::new();
+                if let Some(ch) = e
+                    .resolve_char_ref()
+                    .map_err(|e| OoxmlError::Xml(e.to_string()))?
+                {
+                    text.push(ch);
+                } else {
+                    let name = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    if let Some(resolved) = quick_xml::escape::resolve_predefined_entity(&name) {
+                        text.push_str(resolved);
+                    }
+                }
+                push_revision_text(
+                    &mut result,
+                    &mut open_markup,
+                    mode,
+                    inserted,
+                    deleted,
+                    &text,
+                );
+            },
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"ins" | b"moveTo" => inserted = inserted.saturating_sub(1),
+                b"del" | b"moveFrom" => deleted = deleted.saturating_sub(1),
+                b"t" | b"delText" => in_text = false,
+                _ => {},
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+            _ => {},
+        }
+    }
+
+    close_markup(&mut result, &mut open_markup);
+    Ok(result)
+}
+
+/// Append a piece of text found at the given revision nesting depths.
+fn push_revision_text(
+    result: &mut String,
+    open_markup: &mut Option<bool>,
+    mode: RevisionMode,
+    inserted: usize,
+    deleted: usize,
+    text: &str,
+) {
+    match mode {
+        RevisionMode::AcceptAll if deleted > 0 => return,
+        RevisionMode::RejectAll if inserted > 0 => return,
+        RevisionMode::ShowMarkup => {
+            // Deletions win when both apply, as the text no longer exists
+            let markup = if deleted > 0 {
+                Some(false)
+            } else if inserted > 0 {
+                Some(true)
+            } else {
+                None
+            };
+            if markup != *open_markup {
+                close_markup(result, open_markup);
+                match markup {
+                    Some(true) => result.push_str("{++"),
+                    Some(false) => result.push_str("{--"),
+                    None => {},
+                }
+                *open_markup = markup;
+            }
+        },
+        _ => {},
+    }
+    result.push_str(text);
+}
+
+/// Close the CriticMarkup span opened by [`revision_text`], if any.
+#[inline]
+fn close_markup(result: &mut String, open_markup: &mut Option<bool>) {
+    match open_markup.take() {
+        Some(true) => result.push_str("++}"),
+        Some(false) => result.push_str("--}"),
+        None => {},
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -411,4 +584,59 @@ mod tests {
         assert_eq!(format!("{}", RevisionType::MoveTo), "Move To");
         assert_eq!(format!("{}", RevisionType::FormatChange), "Format Change");
     }
+
+    #[test]
+    fn test_parse_nested_and_paragraph_mark_revisions() {
+        let xml = br#"<w:p>
+            <w:pPr><w:rPr><w:ins w:id="0" w:author="A"/></w:rPr></w:pPr>
+            <w:r><w:t>kept</w:t></w:r>
+            <w:ins w:id="1" w:author="B">
+                <w:r>
+                    <w:rPr><w:rPrChange w:id="2" w:author="C"><w:rPr/></w:rPrChange></w:rPr>
+                    <w:t>added</w:t>
+                </w:r>
+            </w:ins>
+        </w:p>"#;
+
+        let revisions = parse_revisions(xml).unwrap();
+        assert_eq!(revisions.len(), 3);
+        assert_eq!(revisions[0].author(), "A");
+        assert_eq!(revisions[0].text(), "");
+        assert_eq!(revisions[1].revision_type(), RevisionType::FormatChange);
+        assert_eq!(revisions[2].author(), "B");
+        assert_eq!(revisions[2].text(), "added");
+    }
+
+    #[test]
+    fn test_revision_text_modes() {
+        let xml = br#"<w:body>
+            <w:p>
+                <w:r><w:t xml:space="preserve">The </w:t></w:r>
+                <w:del w:id="1" w:author="A"><w:r><w:delText>old</w:delText></w:r></w:del>
+                <w:ins w:id="2" w:author="A"><w:r><w:t>new</w:t></w:r></w:ins>
+                <w:r><w:t xml:space="preserve"> text &amp; more</w:t></w:r>
+            </w:p>
+            <w:p>
+                <w:moveFrom w:id="3" w:author="B"><w:r><w:t>moved</w:t></w:r></w:moveFrom>
+                <w:r><w:t>end</w:t></w:r>
+            </w:p>
+        </w:body>"#;
+
+        assert_eq!(
+            revision_text(xml, RevisionMode::AcceptAll, true).unwrap(),
+            "The new text & more\nend"
+        );
+        assert_eq!(
+            revision_text(xml, RevisionMode::RejectAll, true).unwrap(),
+            "The old text & more\nmovedend"
+        );
+        assert_eq!(
+            revision_text(xml, RevisionMode::ShowMarkup, true).unwrap(),
+            "The {--old--}{++new++} text & more\n{--moved--}end"
+        );
+        assert_eq!(
+            revision_text(xml, RevisionMode::AcceptAll, false).unwrap(),
+            "The new text & moreend"
+        );
+    }
 }
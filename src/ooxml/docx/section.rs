@@ -1,8 +1,6 @@
-use crate::common::unit::{
-    EMUS_PER_CM, EMUS_PER_INCH, EMUS_PER_PT, EMUS_PER_TWIP, emu_to_twip_i64,
-};
+use crate::common::{Length, LengthUnit};
 /// Section - document section with page setup and layout properties.
-use crate::ooxml::docx::enums::{WdOrientation, WdSectionStart};
+use crate::ooxml::docx::enums::{WdHeaderFooter, WdOrientation, WdSectionStart};
 use crate::ooxml::error::Result;
 use quick_xml::Reader;
 use quick_xml::events::Event;
@@ -17,50 +15,56 @@ pub struct Emu(pub i64);
 impl Emu {
     /// Create from inches.
     #[inline]
-    pub const fn from_inches(inches: f64) -> Self {
-        Self((inches * EMUS_PER_INCH as f64) as i64)
+    pub fn from_inches(inches: f64) -> Self {
+        Self::from_unit(inches, LengthUnit::Inch)
     }
 
     /// Create from centimeters.
     #[inline]
-    pub const fn from_cm(cm: f64) -> Self {
-        Self((cm * EMUS_PER_CM as f64) as i64)
+    pub fn from_cm(cm: f64) -> Self {
+        Self::from_unit(cm, LengthUnit::Centimeter)
     }
 
     /// Create from points (1/72 of an inch).
     #[inline]
-    pub const fn from_pt(pt: f64) -> Self {
-        Self((pt * EMUS_PER_PT as f64) as i64)
+    pub fn from_pt(pt: f64) -> Self {
+        Self::from_unit(pt, LengthUnit::Point)
     }
 
     /// Create from twips (1/20 of a point, 1/1440 of an inch).
     #[inline]
     pub const fn from_twips(twips: i64) -> Self {
-        Self(twips * EMUS_PER_TWIP)
+        Self(Length::from_twips(twips).emus())
+    }
+
+    /// Create from a value in any unit.
+    #[inline]
+    pub fn from_unit(value: f64, unit: LengthUnit) -> Self {
+        Self::from(Length::from_unit(value, unit))
     }
 
     /// Convert to inches.
     #[inline]
     pub fn to_inches(self) -> f64 {
-        self.0 as f64 / EMUS_PER_INCH as f64
+        Length::from(self).inches()
     }
 
     /// Convert to centimeters.
     #[inline]
     pub fn to_cm(self) -> f64 {
-        self.0 as f64 / EMUS_PER_CM as f64
+        Length::from(self).cm()
     }
 
     /// Convert to points.
     #[inline]
     pub fn to_pt(self) -> f64 {
-        self.0 as f64 / EMUS_PER_PT as f64
+        Length::from(self).points()
     }
 
     /// Convert to twips.
     #[inline]
     pub fn to_twips(self) -> i64 {
-        emu_to_twip_i64(self.0)
+        Length::from(self).twips()
     }
 }
 
@@ -71,6 +75,20 @@ impl From<i64> for Emu {
     }
 }
 
+impl From<Length> for Emu {
+    #[inline]
+    fn from(length: Length) -> Self {
+        Self(length.emus())
+    }
+}
+
+impl From<Emu> for Length {
+    #[inline]
+    fn from(emu: Emu) -> Self {
+        Length::from_emus(emu.0)
+    }
+}
+
 /// Page margins for a section.
 ///
 /// All measurements are in EMUs (English Metric Units).
@@ -242,6 +260,108 @@ impl Section {
         self.start_type.unwrap_or_default()
     }
 
+    /// Get the headers referenced by this section.
+    ///
+    /// Returns the header type and relationship ID of each
+    /// `<w:headerReference>`. A section without a reference of some type
+    /// continues to use the header of the previous section.
+    pub fn header_references(&self) -> Vec<(WdHeaderFooter, String)> {
+        self.references(b"headerReference")
+    }
+
+    /// Get the footers referenced by this section.
+    ///
+    /// See [`Section::header_references`] for details.
+    pub fn footer_references(&self) -> Vec<(WdHeaderFooter, String)> {
+        self.references(b"footerReference")
+    }
+
+    /// Get the number of text columns of this section.
+    ///
+    /// Returns 1 if the section does not specify columns.
+    pub fn column_count(&self) -> u32 {
+        self.attribute(b"cols", b"num")
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(1)
+    }
+
+    /// Get the space between the text columns of this section.
+    pub fn column_spacing(&self) -> Option<Emu> {
+        self.attribute(b"cols", b"space")
+            .and_then(|value| value.parse().ok())
+            .map(Emu::from_twips)
+    }
+
+    /// Get the page number this section restarts numbering at.
+    ///
+    /// Returns `None` if page numbering continues from the previous section.
+    pub fn page_number_start(&self) -> Option<u32> {
+        self.attribute(b"pgNumType", b"start")
+            .and_then(|value| value.parse().ok())
+    }
+
+    /// Get the page number format of this section (e.g. `lowerRoman`).
+    pub fn page_number_format(&self) -> Option<String> {
+        self.attribute(b"pgNumType", b"fmt")
+    }
+
+    /// Get an attribute of the first child element with the given local name.
+    fn attribute(&self, element: &[u8], name: &[u8]) -> Option<String> {
+        let mut reader = Reader::from_reader(self.xml_bytes.as_slice());
+        reader.config_mut().trim_text(true);
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Empty(e) | Event::Start(e)) if e.local_name().as_ref() == element => {
+                    return e
+                        .attributes()
+                        .flatten()
+                        .find(|attr| attr.key.local_name().as_ref() == name)
+                        .and_then(|attr| attr.decode_and_unescape_value(reader.decoder()).ok())
+                        .map(|value| value.into_owned());
+                },
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => {},
+            }
+        }
+    }
+
+    /// Collect the type and relationship ID of header or footer references.
+    fn references(&self, element: &[u8]) -> Vec<(WdHeaderFooter, String)> {
+        let mut references = Vec::new();
+        let mut reader = Reader::from_reader(self.xml_bytes.as_slice());
+        reader.config_mut().trim_text(true);
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Empty(e) | Event::Start(e)) if e.local_name().as_ref() == element => {
+                    let mut ref_type = WdHeaderFooter::Primary;
+                    let mut r_id = None;
+                    for attr in e.attributes().flatten() {
+                        let Ok(value) = attr.decode_and_unescape_value(reader.decoder()) else {
+                            continue;
+                        };
+                        match attr.key.local_name().as_ref() {
+                            b"type" => {
+                                ref_type = WdHeaderFooter::from_xml(&value).unwrap_or_default()
+                            },
+                            b"id" => r_id = Some(value.into_owned()),
+                            _ => {},
+                        }
+                    }
+                    if let Some(r_id) = r_id {
+                        references.push((ref_type, r_id));
+                    }
+                },
+                Ok(Event::Eof) => break,
+                Err(_) => break,
+                _ => {},
+            }
+        }
+
+        references
+    }
+
     /// Parse page size from the XML if not already cached.
     fn ensure_page_size_parsed(&mut self) {
         if self.page_size.is_some() {
@@ -514,6 +634,46 @@ mod tests {
         assert_eq!(orientation, WdOrientation::Portrait);
     }
 
+    #[test]
+    fn test_header_footer_references() {
+        let section = Section::from_xml_bytes(
+            br#"<w:sectPr><w:headerReference w:type="default" r:id="rId7"/><w:headerReference w:type="first" r:id="rId8"/><w:footerReference w:type="even" r:id="rId9"/><w:pgSz w:w="12240" w:h="15840"/></w:sectPr>"#
+                .to_vec(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            section.header_references(),
+            vec![
+                (WdHeaderFooter::Primary, "rId7".to_string()),
+                (WdHeaderFooter::FirstPage, "rId8".to_string())
+            ]
+        );
+        assert_eq!(
+            section.footer_references(),
+            vec![(WdHeaderFooter::EvenPage, "rId9".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_columns_and_page_numbering() {
+        let section = Section::from_xml_bytes(
+            br#"<w:sectPr><w:pgSz w:w="15840" w:h="12240" w:orient="landscape"/><w:pgNumType w:fmt="lowerRoman" w:start="1"/><w:cols w:num="2" w:space="360"/></w:sectPr>"#
+                .to_vec(),
+        )
+        .unwrap();
+        assert_eq!(section.column_count(), 2);
+        assert_eq!(section.column_spacing(), Some(Emu::from_twips(360)));
+        assert_eq!(section.page_number_start(), Some(1));
+        assert_eq!(section.page_number_format().as_deref(), Some("lowerRoman"));
+
+        let section = Section::from_xml_bytes(br#"<w:sectPr/>"#.to_vec()).unwrap();
+        assert_eq!(section.column_count(), 1);
+        assert_eq!(section.column_spacing(), None);
+        assert_eq!(section.page_number_start(), None);
+        assert_eq!(section.page_number_format(), None);
+    }
+
     #[test]
     fn test_sections_collection() {
         let sections = Sections::new(vec![]);
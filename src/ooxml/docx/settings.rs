@@ -31,6 +31,12 @@ pub struct DocumentSettings {
     protected: bool,
     /// Type of protection
     protection_type: Option<ProtectionType>,
+    /// Whether the editing restriction has a password
+    protection_password: bool,
+    /// Whether the document should be opened read-only
+    read_only_recommended: bool,
+    /// Whether a password is needed to save changes
+    write_password: bool,
     /// Whether to track revisions
     track_revisions: bool,
     /// Zoom percentage
@@ -79,6 +85,9 @@ impl DocumentSettings {
         Self {
             protected: false,
             protection_type: None,
+            protection_password: false,
+            read_only_recommended: false,
+            write_password: false,
             track_revisions: false,
             zoom_percent: None,
         }
@@ -96,6 +105,26 @@ impl DocumentSettings {
         self.protection_type
     }
 
+    /// Check if the editing restriction is lifted by a password.
+    #[inline]
+    pub fn has_protection_password(&self) -> bool {
+        self.protection_password
+    }
+
+    /// Check if the document asks to be opened read-only
+    /// (`w:writeProtection/@w:recommended`).
+    #[inline]
+    pub fn is_read_only_recommended(&self) -> bool {
+        self.read_only_recommended
+    }
+
+    /// Check if a password is needed to save changes to the document
+    /// (`w:writeProtection` with a password hash).
+    #[inline]
+    pub fn has_write_password(&self) -> bool {
+        self.write_password
+    }
+
     /// Check if track revisions is enabled.
     #[inline]
     pub fn track_revisions(&self) -> bool {
@@ -118,7 +147,7 @@ impl DocumentSettings {
     ///
     /// A DocumentSettings object
     pub(crate) fn extract_from_part(part: &dyn Part) -> Result<Self> {
-        let xml_bytes = part.blob();
+        let xml_bytes = part.try_blob()?;
         let mut reader = Reader::from_reader(xml_bytes);
         reader.config_mut().trim_text(true);
 
@@ -144,6 +173,23 @@ impl DocumentSettings {
                                             settings.protected = false;
                                         }
                                     },
+                                    b"hash" | b"hashValue" => {
+                                        settings.protection_password = !attr.value.is_empty();
+                                    },
+                                    _ => {},
+                                }
+                            }
+                        },
+                        b"writeProtection" => {
+                            for attr in e.attributes().flatten() {
+                                match attr.key.local_name().as_ref() {
+                                    b"recommended" => {
+                                        settings.read_only_recommended =
+                                            matches!(attr.value.as_ref(), b"true" | b"1" | b"on");
+                                    },
+                                    b"hash" | b"hashValue" => {
+                                        settings.write_password = !attr.value.is_empty();
+                                    },
                                     _ => {},
                                 }
                             }
@@ -222,4 +268,26 @@ mod tests {
         );
         assert_eq!(ProtectionType::from_xml("invalid"), None);
     }
+
+    #[test]
+    fn test_protection_passwords() {
+        use crate::ooxml::opc::packuri::PackURI;
+        use crate::ooxml::opc::part::BlobPart;
+
+        let xml = br#"<w:settings xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:writeProtection w:recommended="1" w:cryptProviderType="rsaAES" w:hashValue="AAAA" w:saltValue="BBBB"/>
+            <w:documentProtection w:edit="comments" w:enforcement="1"/>
+        </w:settings>"#;
+        let part = BlobPart::new(
+            PackURI::new("/word/settings.xml").unwrap(),
+            "application/xml".to_string(),
+            xml.to_vec(),
+        );
+        let settings = DocumentSettings::extract_from_part(&part).unwrap();
+        assert!(settings.is_protected());
+        assert_eq!(settings.protection_type(), Some(ProtectionType::Comments));
+        assert!(!settings.has_protection_password());
+        assert!(settings.is_read_only_recommended());
+        assert!(settings.has_write_password());
+    }
 }
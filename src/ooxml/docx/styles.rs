@@ -1,10 +1,14 @@
 /// Styles - document styles and formatting definitions.
+use crate::common::Language;
 use crate::ooxml::docx::enums::WdStyleType;
+use crate::ooxml::docx::paragraph::parse_color;
+use crate::ooxml::drawings::color::ColorSpec;
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::part::Part;
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 use smallvec::SmallVec;
+use std::collections::HashMap;
 
 /// A collection of styles defined in a Word document.
 ///
@@ -31,6 +35,8 @@ pub struct Styles<'a> {
     part: &'a dyn Part,
     /// Cached list of styles
     style_list: Option<SmallVec<[Style; 32]>>,
+    /// Cached run formatting of the document defaults (`w:rPrDefault`)
+    defaults: CharacterFormat,
 }
 
 impl<'a> std::fmt::Debug for Styles<'a> {
@@ -53,6 +59,7 @@ impl<'a> Styles<'a> {
         Self {
             part,
             style_list: None,
+            defaults: CharacterFormat::default(),
         }
     }
 
@@ -109,21 +116,184 @@ impl<'a> Styles<'a> {
         }))
     }
 
+    /// Resolve the outline level of a paragraph style.
+    ///
+    /// Follows the `basedOn` chain until a style defines `w:outlineLvl`.
+    /// Returns `None` for body text or unknown styles.
+    pub fn outline_level(&mut self, style_id: &str) -> Result<Option<u8>> {
+        self.ensure_styles_loaded()?;
+        let Some(list) = self.style_list.as_ref() else {
+            return Ok(None);
+        };
+
+        let mut current = Some(style_id);
+        // Guard against cycles in malformed basedOn chains
+        for _ in 0..list.len() {
+            let Some(style) = current.and_then(|id| list.iter().find(|s| s.style_id == id)) else {
+                break;
+            };
+            if let Some(level) = style.outline_level {
+                return Ok((level < 9).then_some(level));
+            }
+            current = style.based_on.as_deref();
+        }
+        Ok(None)
+    }
+
+    /// Resolve the numbering of a paragraph style.
+    ///
+    /// Follows the `basedOn` chain until a style defines `w:numId`, and
+    /// returns it with the list level (0 if none is set). Returns `None` for
+    /// styles without numbering.
+    pub fn numbering(&mut self, style_id: &str) -> Result<Option<(u32, u8)>> {
+        self.ensure_styles_loaded()?;
+        let Some(list) = self.style_list.as_ref() else {
+            return Ok(None);
+        };
+
+        let mut level = None;
+        let mut current = Some(style_id);
+        // Guard against cycles in malformed basedOn chains
+        for _ in 0..list.len() {
+            let Some(style) = current.and_then(|id| list.iter().find(|s| s.style_id == id)) else {
+                break;
+            };
+            level = level.or(style.numbering_level);
+            if let Some(num_id) = style.num_id {
+                return Ok(Some((num_id, level.unwrap_or(0))));
+            }
+            current = style.based_on.as_deref();
+        }
+        Ok(None)
+    }
+
+    /// Get the run formatting of the document defaults (`w:docDefaults`).
+    pub fn default_character_format(&mut self) -> Result<&CharacterFormat> {
+        self.ensure_styles_loaded()?;
+        Ok(&self.defaults)
+    }
+
+    /// Resolve the character formatting of text with the given paragraph
+    /// and character styles, before any direct run formatting.
+    ///
+    /// Properties are taken from, in increasing priority: the document
+    /// defaults, the paragraph style and the styles it is based on, then
+    /// the character style and the styles it is based on. A missing or
+    /// unknown paragraph style stands for the default paragraph style.
+    ///
+    /// See [`Run::effective_format`](crate::ooxml::docx::Run::effective_format)
+    /// to include the formatting of a run.
+    pub fn resolve_character_format(
+        &mut self,
+        paragraph_style: Option<&str>,
+        character_style: Option<&str>,
+    ) -> Result<CharacterFormat> {
+        self.ensure_styles_loaded()?;
+        let mut format = self.defaults.clone();
+        let Some(list) = self.style_list.as_ref() else {
+            return Ok(format);
+        };
+
+        let find = |id: &str| list.iter().find(|s| s.style_id == id);
+        let paragraph = paragraph_style.and_then(&find).or_else(|| {
+            list.iter()
+                .find(|s| s.is_default && s.style_type == WdStyleType::Paragraph)
+        });
+        let character = character_style.and_then(&find);
+
+        for style in [paragraph, character].into_iter().flatten() {
+            apply_style_chain(list, style, &mut format);
+        }
+        Ok(format)
+    }
+
+    /// Get the document default languages (`w:lang`), including those of
+    /// the default paragraph style.
+    pub fn default_language(&mut self) -> Result<Language> {
+        Ok(self.resolve_character_format(None, None)?.language)
+    }
+
+    /// Resolve the languages of every paragraph and character style, so
+    /// run languages can be resolved without the styles part.
+    pub(crate) fn languages(&mut self) -> Result<StyleLanguages> {
+        let mut languages = StyleLanguages {
+            defaults: self.default_language()?,
+            ..Default::default()
+        };
+        let Some(list) = self.style_list.as_ref() else {
+            return Ok(languages);
+        };
+
+        for style in list.iter() {
+            let map = match style.style_type {
+                WdStyleType::Paragraph => &mut languages.paragraph,
+                WdStyleType::Character => &mut languages.character,
+                _ => continue,
+            };
+            // Paragraph styles include the defaults below them, character
+            // styles only their own chain
+            let mut format = match style.style_type {
+                WdStyleType::Paragraph => self.defaults.clone(),
+                _ => CharacterFormat::default(),
+            };
+            apply_style_chain(list, style, &mut format);
+            map.insert(style.style_id.clone(), format.language);
+        }
+        Ok(languages)
+    }
+
+    #[inline]
+    fn val_u32(e: &BytesStart<'_>) -> Option<u32> {
+        e.attributes()
+            .flatten()
+            .find(|attr| attr.key.local_name().as_ref() == b"val")
+            .and_then(|attr| std::str::from_utf8(&attr.value).ok()?.parse().ok())
+    }
+
     /// Ensure styles are loaded from XML.
     fn ensure_styles_loaded(&mut self) -> Result<()> {
         if self.style_list.is_some() {
             return Ok(());
         }
 
-        let xml_bytes = self.part.blob();
+        let xml_bytes = self.part.try_blob()?;
         let mut reader = Reader::from_reader(xml_bytes);
         reader.config_mut().trim_text(true);
 
         let mut styles = SmallVec::new();
         let mut current_style: Option<StyleBuilder> = None;
+        // Run properties of the document defaults or of the current style,
+        // leaving out those of the paragraph mark and of table conditions
+        let mut in_defaults = false;
+        let mut skip_depth = 0usize;
+        let mut in_run_properties = false;
 
         loop {
             match reader.read_event() {
+                Ok(Event::Start(e))
+                    if matches!(e.local_name().as_ref(), b"pPr" | b"tblStylePr") =>
+                {
+                    skip_depth += 1;
+                },
+                Ok(Event::End(e)) if matches!(e.local_name().as_ref(), b"pPr" | b"tblStylePr") => {
+                    skip_depth = skip_depth.saturating_sub(1);
+                },
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"rPrDefault" => {
+                    in_defaults = true;
+                },
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"rPrDefault" => {
+                    in_defaults = false;
+                },
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"rPr" => {
+                    in_run_properties = skip_depth == 0 && (in_defaults || current_style.is_some());
+                },
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"rPr" => {
+                    in_run_properties = false;
+                },
+                Ok(Event::Empty(e)) if in_run_properties => match current_style.as_mut() {
+                    Some(builder) => builder.character_format.apply_element(&e),
+                    None => self.defaults.apply_element(&e),
+                },
                 Ok(Event::Start(e)) if e.local_name().as_ref() == b"style" => {
                     // Start a new style
                     let mut builder = StyleBuilder::default();
@@ -176,6 +346,12 @@ impl<'a> Styles<'a> {
                                 }
                             }
                         },
+                        b"next" => {
+                            builder.next = attr_val(&e);
+                        },
+                        b"link" => {
+                            builder.link = attr_val(&e);
+                        },
                         b"basedOn" => {
                             // Parse basedOn attribute
                             for attr in e.attributes().flatten() {
@@ -199,6 +375,23 @@ impl<'a> Styles<'a> {
                                 }
                             }
                         },
+                        b"outlineLvl" => {
+                            for attr in e.attributes().flatten() {
+                                if attr.key.local_name().as_ref() == b"val"
+                                    && let Ok(level) = std::str::from_utf8(&attr.value)
+                                        .unwrap_or_default()
+                                        .parse::<u8>()
+                                {
+                                    builder.outline_level = Some(level);
+                                }
+                            }
+                        },
+                        b"numId" => {
+                            builder.num_id = Self::val_u32(&e);
+                        },
+                        b"ilvl" => {
+                            builder.numbering_level = Self::val_u32(&e).map(|level| level as u8);
+                        },
                         b"qFormat" => {
                             builder.is_quick_style = true;
                         },
@@ -227,6 +420,12 @@ impl<'a> Styles<'a> {
                             is_quick_style: builder.is_quick_style,
                             is_hidden: builder.is_hidden,
                             is_locked: builder.is_locked,
+                            outline_level: builder.outline_level,
+                            num_id: builder.num_id,
+                            numbering_level: builder.numbering_level,
+                            next: builder.next,
+                            link: builder.link,
+                            character_format: builder.character_format,
                         });
                     }
                 },
@@ -241,6 +440,78 @@ impl<'a> Styles<'a> {
     }
 }
 
+/// Apply the run formatting of `style` and the styles it is based on, from
+/// the root of the chain down to the style itself.
+fn apply_style_chain(list: &[Style], style: &Style, format: &mut CharacterFormat) {
+    let find = |id: &str| list.iter().find(|s| s.style_id == id);
+    // Guard against cycles in malformed chains
+    let mut chain = vec![style];
+    while chain.len() < list.len()
+        && let Some(base) = chain[chain.len() - 1].based_on.as_deref().and_then(find)
+    {
+        chain.push(base);
+    }
+    for style in chain.iter().rev() {
+        format.apply(&style.character_format);
+    }
+}
+
+/// The resolved languages of the styles of a document.
+///
+/// Attached to body paragraphs by the document so that
+/// [`Run::language`](crate::ooxml::docx::Run::language) can take styles
+/// into account.
+#[derive(Debug, Default)]
+pub(crate) struct StyleLanguages {
+    /// Document defaults and default paragraph style
+    defaults: Language,
+    /// Paragraph styles by ID, including the document defaults
+    paragraph: HashMap<String, Language>,
+    /// Character styles by ID, without the document defaults
+    character: HashMap<String, Language>,
+}
+
+impl StyleLanguages {
+    /// Check whether no style declares a language.
+    pub fn is_empty(&self) -> bool {
+        self.defaults.is_empty()
+            && self.paragraph.values().all(Language::is_empty)
+            && self.character.values().all(Language::is_empty)
+    }
+
+    /// Get the languages of a paragraph style. A missing or unknown style
+    /// stands for the default paragraph style.
+    pub fn paragraph(&self, style_id: Option<&str>) -> &Language {
+        style_id
+            .and_then(|id| self.paragraph.get(id))
+            .unwrap_or(&self.defaults)
+    }
+
+    /// Get the languages of a character style.
+    pub fn character(&self, style_id: &str) -> Option<&Language> {
+        self.character.get(style_id)
+    }
+}
+
+/// Get the languages of a `w:lang` element.
+pub(crate) fn parse_lang(e: &BytesStart<'_>) -> Language {
+    let mut language = Language::default();
+    for attr in e.attributes().flatten() {
+        let slot = match attr.key.local_name().as_ref() {
+            b"val" => &mut language.latin,
+            b"eastAsia" => &mut language.east_asian,
+            b"bidi" => &mut language.complex,
+            _ => continue,
+        };
+        *slot = attr
+            .unescape_value()
+            .ok()
+            .filter(|value| !value.is_empty())
+            .map(|value| value.into_owned());
+    }
+    language
+}
+
 /// Builder for constructing Style objects during XML parsing.
 #[derive(Debug, Default)]
 struct StyleBuilder {
@@ -254,6 +525,90 @@ struct StyleBuilder {
     is_quick_style: bool,
     is_hidden: bool,
     is_locked: bool,
+    outline_level: Option<u8>,
+    num_id: Option<u32>,
+    numbering_level: Option<u8>,
+    next: Option<String>,
+    link: Option<String>,
+    character_format: CharacterFormat,
+}
+
+/// Get the unescaped `w:val` attribute of an element.
+fn attr_val(e: &BytesStart<'_>) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == b"val")
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+/// Character formatting defined by a style, the document defaults or a run.
+///
+/// `None` means the property is not set at that level and is inherited.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CharacterFormat {
+    /// Bold (`w:b`)
+    pub bold: Option<bool>,
+    /// Italic (`w:i`)
+    pub italic: Option<bool>,
+    /// Font name (`w:rFonts/@w:ascii`)
+    pub font_name: Option<String>,
+    /// Font size in half-points (`w:sz`)
+    pub font_size: Option<u32>,
+    /// Text color (`w:color`)
+    pub color: Option<ColorSpec>,
+    /// Languages (`w:lang`)
+    pub language: Language,
+}
+
+impl CharacterFormat {
+    /// Override the properties set in `other`.
+    pub fn apply(&mut self, other: &CharacterFormat) {
+        if other.bold.is_some() {
+            self.bold = other.bold;
+        }
+        if other.italic.is_some() {
+            self.italic = other.italic;
+        }
+        if other.font_name.is_some() {
+            self.font_name.clone_from(&other.font_name);
+        }
+        if other.font_size.is_some() {
+            self.font_size = other.font_size;
+        }
+        if other.color.is_some() {
+            self.color.clone_from(&other.color);
+        }
+        self.language.apply(&other.language);
+    }
+
+    /// Set the property of a run properties child element.
+    fn apply_element(&mut self, e: &BytesStart<'_>) {
+        match e.local_name().as_ref() {
+            b"b" => self.bold = Some(toggle_value(e)),
+            b"i" => self.italic = Some(toggle_value(e)),
+            b"sz" => self.font_size = Styles::val_u32(e).or(self.font_size),
+            b"color" => self.color = parse_color(e).or(self.color.take()),
+            b"lang" => self.language.apply(&parse_lang(e)),
+            b"rFonts" => {
+                if let Some(name) = e
+                    .attributes()
+                    .flatten()
+                    .find(|attr| attr.key.local_name().as_ref() == b"ascii")
+                    .and_then(|attr| attr.unescape_value().ok())
+                {
+                    self.font_name = Some(name.into_owned());
+                }
+            },
+            _ => {},
+        }
+    }
+}
+
+/// Get the value of a toggle property element such as `<w:b/>`, which is
+/// on unless `w:val` says otherwise.
+fn toggle_value(e: &BytesStart<'_>) -> bool {
+    attr_val(e).is_none_or(|value| matches!(value.as_str(), "1" | "true" | "on"))
 }
 
 /// A single style definition in a Word document.
@@ -282,6 +637,18 @@ pub struct Style {
     is_hidden: bool,
     /// Whether locked (formatting protection)
     is_locked: bool,
+    /// Outline level from the style's paragraph properties
+    outline_level: Option<u8>,
+    /// Numbering instance from the style's paragraph properties
+    num_id: Option<u32>,
+    /// List level from the style's paragraph properties
+    numbering_level: Option<u8>,
+    /// ID of the style of the following paragraph
+    next: Option<String>,
+    /// ID of the linked style
+    link: Option<String>,
+    /// Run formatting defined directly by this style
+    character_format: CharacterFormat,
 }
 
 impl Style {
@@ -358,6 +725,49 @@ impl Style {
     pub fn is_locked(&self) -> bool {
         self.is_locked
     }
+
+    /// Get the outline level defined directly by this style (`w:outlineLvl`).
+    ///
+    /// Levels are 0-based: 0 is the level of the built-in "Heading 1" style
+    /// in every locale. Level 9 means body text.
+    #[inline]
+    pub fn outline_level(&self) -> Option<u8> {
+        self.outline_level
+    }
+
+    /// Get the numbering instance defined directly by this style (`w:numId`).
+    #[inline]
+    pub fn num_id(&self) -> Option<u32> {
+        self.num_id
+    }
+
+    /// Get the list level defined directly by this style (`w:ilvl`), 0-based.
+    #[inline]
+    pub fn numbering_level(&self) -> Option<u8> {
+        self.numbering_level
+    }
+
+    /// Get the ID of the style of the paragraph following one with this
+    /// style (`w:next`).
+    #[inline]
+    pub fn next_style(&self) -> Option<&str> {
+        self.next.as_deref()
+    }
+
+    /// Get the ID of the linked paragraph or character style (`w:link`).
+    #[inline]
+    pub fn linked_style(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
+    /// Get the run formatting defined directly by this style, without the
+    /// formatting inherited from its base styles.
+    ///
+    /// See [`Styles::resolve_character_format`] for the inherited formatting.
+    #[inline]
+    pub fn character_format(&self) -> &CharacterFormat {
+        &self.character_format
+    }
 }
 
 #[cfg(test)]
@@ -369,4 +779,76 @@ mod tests {
         let style_type = WdStyleType::default();
         assert_eq!(style_type, WdStyleType::Paragraph);
     }
+
+    #[test]
+    fn test_resolve_character_format_through_style_chain() {
+        use crate::common::RGBColor;
+        use crate::ooxml::opc::packuri::PackURI;
+        use crate::ooxml::opc::part::BlobPart;
+
+        let xml = br#"<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri"/><w:sz w:val="22"/></w:rPr></w:rPrDefault></w:docDefaults>
+<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/><w:rPr><w:color w:val="333333"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Base"><w:name w:val="Base"/><w:basedOn w:val="Normal"/><w:rPr><w:rFonts w:ascii="Georgia"/><w:b/><w:sz w:val="24"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Middle"><w:name w:val="Middle"/><w:basedOn w:val="Base"/><w:next w:val="Base"/><w:link w:val="MiddleChar"/><w:pPr><w:rPr><w:sz w:val="99"/></w:rPr></w:pPr><w:rPr><w:i/><w:color w:val="FF0000"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Top"><w:name w:val="Top"/><w:basedOn w:val="Middle"/><w:rPr><w:b w:val="0"/><w:sz w:val="28"/></w:rPr></w:style>
+<w:style w:type="character" w:styleId="Accent"><w:name w:val="Accent"/><w:rPr><w:b/><w:color w:val="0000FF"/></w:rPr></w:style>
+</w:styles>"#;
+        let part = BlobPart::new(
+            PackURI::new("/word/styles.xml").unwrap(),
+            "application/xml".to_string(),
+            xml.to_vec(),
+        );
+        let mut styles = Styles::from_part(&part);
+
+        let defaults = styles.default_character_format().unwrap();
+        assert_eq!(defaults.font_name.as_deref(), Some("Calibri"));
+        assert_eq!(defaults.font_size, Some(22));
+
+        let middle = styles.get_by_id("Middle").unwrap().unwrap();
+        assert_eq!(middle.next_style(), Some("Base"));
+        assert_eq!(middle.linked_style(), Some("MiddleChar"));
+        // The paragraph mark formatting is not part of the style's run formatting
+        assert_eq!(middle.character_format().font_size, None);
+
+        // Each level overrides the one below: defaults < Normal < Base < Middle < Top
+        let top = styles.resolve_character_format(Some("Top"), None).unwrap();
+        assert_eq!(top.bold, Some(false));
+        assert_eq!(top.italic, Some(true));
+        assert_eq!(top.font_size, Some(28));
+        assert_eq!(top.font_name.as_deref(), Some("Georgia"));
+        assert_eq!(
+            top.color,
+            Some(ColorSpec::Rgb(RGBColor::new(0xFF, 0x00, 0x00)))
+        );
+
+        let base = styles.resolve_character_format(Some("Base"), None).unwrap();
+        assert_eq!(base.bold, Some(true));
+        assert_eq!(base.italic, None);
+        assert_eq!(
+            base.color,
+            Some(ColorSpec::Rgb(RGBColor::new(0x33, 0x33, 0x33)))
+        );
+
+        // The character style wins over the paragraph style
+        let accent = styles
+            .resolve_character_format(Some("Top"), Some("Accent"))
+            .unwrap();
+        assert_eq!(accent.bold, Some(true));
+        assert_eq!(accent.font_size, Some(28));
+        assert_eq!(
+            accent.color,
+            Some(ColorSpec::Rgb(RGBColor::new(0x00, 0x00, 0xFF)))
+        );
+
+        // Unknown paragraph styles fall back to the default paragraph style
+        let unknown = styles
+            .resolve_character_format(Some("Missing"), None)
+            .unwrap();
+        assert_eq!(unknown.font_size, Some(22));
+        assert_eq!(
+            unknown.color,
+            Some(ColorSpec::Rgb(RGBColor::new(0x33, 0x33, 0x33)))
+        );
+    }
 }
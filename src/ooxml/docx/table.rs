@@ -1,5 +1,9 @@
-use crate::common::XmlSlice;
+use crate::common::xml::attr;
+use crate::common::{Length, RGBColor, XmlSlice};
 /// Table, Row, and Cell structures for Word documents.
+use crate::document::{
+    BorderLineStyle, CellBorder, CellBorders, CellVerticalAlignment, DocumentElement,
+};
 use crate::ooxml::docx::paragraph::Paragraph;
 use crate::ooxml::error::{OoxmlError, Result};
 use quick_xml::Reader;
@@ -40,6 +44,35 @@ pub enum VMergeState {
     Continue,
 }
 
+/// Formatting a table cell sets in its own `<w:tcPr>`.
+///
+/// Style and table-level defaults are not applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TableCellProperties {
+    /// Number of grid columns the cell spans (`<w:gridSpan>`)
+    pub grid_span: usize,
+    /// Vertical merge state (`<w:vMerge>`)
+    pub v_merge: Option<VMergeState>,
+    /// Vertical alignment of the content (`<w:vAlign>`)
+    pub vertical_alignment: Option<CellVerticalAlignment>,
+    /// Background color (`<w:shd>`)
+    pub shading: Option<RGBColor>,
+    /// Cell borders (`<w:tcBorders>`)
+    pub borders: CellBorders,
+}
+
+impl Default for TableCellProperties {
+    fn default() -> Self {
+        Self {
+            grid_span: 1,
+            v_merge: None,
+            vertical_alignment: None,
+            shading: None,
+            borders: CellBorders::default(),
+        }
+    }
+}
+
 /// A table in a Word document.
 ///
 /// Represents a `<w:tbl>` element. Tables contain rows, which contain cells,
@@ -106,20 +139,55 @@ impl Table {
         self.xml_data.as_bytes()
     }
 
+    /// Count the paragraphs in this table, including those of nested tables.
+    ///
+    /// Matches how the paragraphs of the document body are counted: only
+    /// `<w:p>` elements that are not inside another paragraph.
+    pub(crate) fn paragraph_count(&self) -> Result<usize> {
+        let mut reader = Reader::from_reader(self.xml_bytes());
+
+        let mut count = 0;
+        let mut para_depth = 0u32;
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"p" => {
+                    if para_depth == 0 {
+                        count += 1;
+                    }
+                    para_depth += 1;
+                },
+                Ok(Event::End(e)) if e.local_name().as_ref() == b"p" => {
+                    para_depth = para_depth.saturating_sub(1);
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(count)
+    }
+
     /// Get the number of rows in this table.
+    ///
+    /// Rows of nested tables are not counted.
     pub fn row_count(&self) -> Result<usize> {
         let mut reader = Reader::from_reader(self.xml_bytes());
         reader.config_mut().trim_text(true);
 
         let mut count = 0;
+        let mut depth = 0usize;
 
         loop {
             match reader.read_event() {
                 Ok(Event::Start(e)) => {
-                    if e.local_name().as_ref() == b"tr" {
+                    depth += 1;
+                    if depth == 2 && e.local_name().as_ref() == b"tr" {
                         count += 1;
                     }
                 },
+                Ok(Event::End(_)) => depth = depth.saturating_sub(1),
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(OoxmlError::Xml(e.to_string())),
                 _ => {},
@@ -141,6 +209,48 @@ impl Table {
         }
     }
 
+    /// Get the width of each grid column from `<w:tblGrid>`.
+    ///
+    /// Only the grid of this table is read, not those of nested tables.
+    pub fn column_widths(&self) -> Result<Vec<Length>> {
+        let mut reader = Reader::from_reader(self.xml_bytes());
+        reader.config_mut().trim_text(true);
+
+        let mut depth = 0usize;
+        let mut in_grid = false;
+        let mut widths = Vec::new();
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    depth += 1;
+                    if depth == 2 && e.local_name().as_ref() == b"tblGrid" {
+                        in_grid = true;
+                    }
+                },
+                Ok(Event::Empty(e))
+                    if in_grid && depth == 2 && e.local_name().as_ref() == b"gridCol" =>
+                {
+                    let twips = attr(&e, b"w")
+                        .and_then(|w| w.parse::<i64>().ok())
+                        .unwrap_or(0);
+                    widths.push(Length::from_twips(twips));
+                },
+                Ok(Event::End(e)) => {
+                    if depth == 2 && e.local_name().as_ref() == b"tblGrid" {
+                        break;
+                    }
+                    depth = depth.saturating_sub(1);
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(widths)
+    }
+
     /// Get all rows in this table.
     ///
     /// # Performance
@@ -289,19 +399,24 @@ impl Row {
     }
 
     /// Get the number of cells in this row.
+    ///
+    /// Cells of nested tables are not counted.
     pub fn cell_count(&self) -> Result<usize> {
         let mut reader = Reader::from_reader(&self.xml_bytes[..]);
         reader.config_mut().trim_text(true);
 
         let mut count = 0;
+        let mut depth = 0usize;
 
         loop {
             match reader.read_event() {
                 Ok(Event::Start(e)) => {
-                    if e.local_name().as_ref() == b"tc" {
+                    depth += 1;
+                    if depth == 2 && e.local_name().as_ref() == b"tc" {
                         count += 1;
                     }
                 },
+                Ok(Event::End(_)) => depth = depth.saturating_sub(1),
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(OoxmlError::Xml(e.to_string())),
                 _ => {},
@@ -311,6 +426,43 @@ impl Row {
         Ok(count)
     }
 
+    /// Get the number of grid columns skipped before the first cell
+    /// (`<w:gridBefore>`).
+    pub fn grid_before(&self) -> Result<usize> {
+        let mut reader = Reader::from_reader(&self.xml_bytes[..]);
+        reader.config_mut().trim_text(true);
+
+        let mut depth = 0usize;
+        let mut in_tr_pr = false;
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    depth += 1;
+                    if depth == 2 && e.local_name().as_ref() == b"trPr" {
+                        in_tr_pr = true;
+                    }
+                },
+                Ok(Event::Empty(e))
+                    if in_tr_pr && depth == 2 && e.local_name().as_ref() == b"gridBefore" =>
+                {
+                    return Ok(attr(&e, b"val").and_then(|v| v.parse().ok()).unwrap_or(0));
+                },
+                Ok(Event::End(e)) => {
+                    if depth == 2 && e.local_name().as_ref() == b"trPr" {
+                        break;
+                    }
+                    depth = depth.saturating_sub(1);
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(0)
+    }
+
     /// Get all cells in this row.
     ///
     /// # Performance
@@ -461,44 +613,7 @@ impl Cell {
     /// </w:tc>
     /// ```
     pub fn grid_span(&self) -> Result<usize> {
-        let mut reader = Reader::from_reader(&self.xml_bytes[..]);
-        reader.config_mut().trim_text(true);
-
-        let mut in_tc_pr = false;
-
-        loop {
-            match reader.read_event() {
-                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
-                    let name = e.local_name();
-                    if name.as_ref() == b"tcPr" {
-                        in_tc_pr = true;
-                    } else if in_tc_pr && name.as_ref() == b"gridSpan" {
-                        // Extract the w:val attribute
-                        for attr in e.attributes().flatten() {
-                            if attr.key.local_name().as_ref() == b"val" {
-                                let val_str = std::str::from_utf8(&attr.value)
-                                    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                                let span = val_str.parse::<usize>().unwrap_or(1);
-                                return Ok(span);
-                            }
-                        }
-                        // If no val attribute, default to 1
-                        return Ok(1);
-                    }
-                },
-                Ok(Event::End(e)) => {
-                    if e.local_name().as_ref() == b"tcPr" {
-                        in_tc_pr = false;
-                    }
-                },
-                Ok(Event::Eof) => break,
-                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
-                _ => {},
-            }
-        }
-
-        // Default: no horizontal merge
-        Ok(1)
+        Ok(self.properties()?.grid_span)
     }
 
     /// Get the vertical merge (rowspan) state of this cell.
@@ -528,51 +643,95 @@ impl Cell {
     /// </w:tc>
     /// ```
     pub fn v_merge(&self) -> Result<Option<VMergeState>> {
+        Ok(self.properties()?.v_merge)
+    }
+
+    /// Get the formatting this cell sets in its `<w:tcPr>`.
+    ///
+    /// Only the cell's own properties are read; cells of nested tables are
+    /// skipped.
+    pub fn properties(&self) -> Result<TableCellProperties> {
         let mut reader = Reader::from_reader(&self.xml_bytes[..]);
         reader.config_mut().trim_text(true);
 
+        let mut props = TableCellProperties::default();
+        // Depth 1 is <w:tc>, 2 is <w:tcPr>, 3 its children and 4 the edges
+        // inside <w:tcBorders>.
+        let mut depth = 0usize;
         let mut in_tc_pr = false;
+        let mut in_borders = false;
 
         loop {
-            match reader.read_event() {
-                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
-                    let name = e.local_name();
-                    if name.as_ref() == b"tcPr" {
-                        in_tc_pr = true;
-                    } else if in_tc_pr && name.as_ref() == b"vMerge" {
-                        // Check for w:val attribute
-                        for attr in e.attributes().flatten() {
-                            if attr.key.local_name().as_ref() == b"val" {
-                                let val_str = std::str::from_utf8(&attr.value)
-                                    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                                return match val_str {
-                                    "restart" => Ok(Some(VMergeState::Restart)),
-                                    _ => Ok(Some(VMergeState::Continue)),
-                                };
-                            }
-                        }
-                        // No val attribute means continue
-                        return Ok(Some(VMergeState::Continue));
-                    }
-                },
+            let (e, empty) = match reader.read_event() {
+                Ok(Event::Start(e)) => (e, false),
+                Ok(Event::Empty(e)) => (e, true),
                 Ok(Event::End(e)) => {
-                    if e.local_name().as_ref() == b"tcPr" {
-                        in_tc_pr = false;
+                    match (depth, e.local_name().as_ref()) {
+                        (2, b"tcPr") => break,
+                        (3, b"tcBorders") => in_borders = false,
+                        _ => {},
                     }
+                    depth = depth.saturating_sub(1);
+                    continue;
                 },
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => continue,
+            };
+            let level = depth + 1;
+            if !empty {
+                depth = level;
+            }
+            let name = e.local_name();
+
+            match level {
+                2 if name.as_ref() == b"tcPr" => in_tc_pr = true,
+                // Content before <w:tcPr> means the cell has no properties
+                2 if !in_tc_pr => break,
+                3 if in_tc_pr => match name.as_ref() {
+                    b"gridSpan" => {
+                        props.grid_span =
+                            attr(&e, b"val").and_then(|v| v.parse().ok()).unwrap_or(1);
+                    },
+                    b"vMerge" => {
+                        props.v_merge = Some(match attr(&e, b"val").as_deref() {
+                            Some("restart") => VMergeState::Restart,
+                            _ => VMergeState::Continue,
+                        });
+                    },
+                    b"vAlign" => {
+                        props.vertical_alignment = match attr(&e, b"val").as_deref() {
+                            Some("top") => Some(CellVerticalAlignment::Top),
+                            Some("center") => Some(CellVerticalAlignment::Center),
+                            Some("bottom") => Some(CellVerticalAlignment::Bottom),
+                            _ => None,
+                        };
+                    },
+                    b"shd" => props.shading = parse_shading(&e),
+                    b"tcBorders" => in_borders = !empty,
+                    _ => {},
+                },
+                4 if in_borders => {
+                    let border = parse_border(&e);
+                    match name.as_ref() {
+                        b"top" => props.borders.top = border,
+                        b"left" | b"start" => props.borders.left = border,
+                        b"bottom" => props.borders.bottom = border,
+                        b"right" | b"end" => props.borders.right = border,
+                        _ => {},
+                    }
+                },
                 _ => {},
             }
         }
 
-        // Default: no vertical merge
-        Ok(None)
+        Ok(props)
     }
 
     /// Get the text content of this cell.
     ///
-    /// Concatenates all text from all paragraphs in the cell.
+    /// Concatenates all text from all paragraphs in the cell, including those
+    /// of nested tables.
     ///
     /// # Performance
     ///
@@ -600,6 +759,12 @@ impl Cell {
 
         loop {
             match reader.read_event() {
+                Ok(Event::Start(e)) if e.local_name().as_ref() == b"txbxContent" => {
+                    // Text box content is reported by `Paragraph::text_boxes`
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                },
                 Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
                     if e.local_name().as_ref() == b"t" {
                         in_text_element = true;
@@ -623,81 +788,100 @@ impl Cell {
         Ok(result)
     }
 
-    /// Get all paragraphs in this cell.
+    /// Get the paragraphs directly in this cell.
+    ///
+    /// Paragraphs of nested tables are left out; see [`Cell::tables`].
     ///
     /// # Performance
     ///
     /// Uses SmallVec for efficient storage of typically small paragraph collections.
     pub fn paragraphs(&self) -> Result<SmallVec<[Paragraph; 8]>> {
-        let mut reader = Reader::from_reader(&self.xml_bytes[..]);
+        Ok(self
+            .blocks()?
+            .into_iter()
+            .filter_map(|block| match block {
+                CellBlock::Paragraph(para) => Some(para),
+                CellBlock::Table(_) => None,
+            })
+            .collect())
+    }
+
+    /// Get the tables nested directly in this cell.
+    pub fn tables(&self) -> Result<Vec<Table>> {
+        Ok(self
+            .blocks()?
+            .into_iter()
+            .filter_map(|block| match block {
+                CellBlock::Table(table) => Some(*table),
+                CellBlock::Paragraph(_) => None,
+            })
+            .collect())
+    }
+
+    /// Get the paragraphs and nested tables of this cell in document order.
+    pub fn elements(&self) -> Result<Vec<DocumentElement>> {
+        Ok(self
+            .blocks()?
+            .into_iter()
+            .map(|block| match block {
+                CellBlock::Paragraph(para) => {
+                    DocumentElement::Paragraph(Box::new(crate::document::Paragraph::Docx(para)))
+                },
+                CellBlock::Table(table) => {
+                    DocumentElement::Table(Box::new(crate::document::Table::Docx(table)))
+                },
+            })
+            .collect())
+    }
+
+    /// Split the cell content into its paragraphs and nested tables.
+    ///
+    /// Content controls and other wrappers are looked through, but the
+    /// paragraphs of a nested table stay inside that table.
+    fn blocks(&self) -> Result<Vec<CellBlock>> {
+        let xml = &self.xml_bytes[..];
+        let mut reader = Reader::from_reader(xml);
         reader.config_mut().trim_text(true);
 
-        // Use SmallVec for efficient storage of paragraph collections
-        let mut paragraphs = SmallVec::new();
-        let mut current_para_xml = Vec::with_capacity(1024); // Pre-allocate for paragraph XML
-        let mut in_para = false;
-        let mut depth = 0;
+        let mut blocks = Vec::new();
+        let mut depth = 0usize;
+        // Start offset and depth of the paragraph or table being read
+        let mut open: Option<(usize, usize, bool)> = None;
 
         loop {
+            let before = reader.buffer_position() as usize;
             match reader.read_event() {
                 Ok(Event::Start(e)) => {
-                    if e.local_name().as_ref() == b"p" && !in_para {
-                        in_para = true;
-                        depth = 1;
-                        current_para_xml.clear();
-                        current_para_xml.extend_from_slice(b"<w:p");
-                        for attr in e.attributes().flatten() {
-                            current_para_xml.push(b' ');
-                            current_para_xml.extend_from_slice(attr.key.as_ref());
-                            current_para_xml.extend_from_slice(b"=\"");
-                            current_para_xml.extend_from_slice(&attr.value);
-                            current_para_xml.push(b'"');
+                    depth += 1;
+                    if open.is_none() && depth >= 2 {
+                        match e.local_name().as_ref() {
+                            b"p" => open = Some((before, depth, false)),
+                            b"tbl" => open = Some((before, depth, true)),
+                            _ => {},
                         }
-                        current_para_xml.push(b'>');
-                    } else if in_para {
-                        depth += 1;
-                        current_para_xml.push(b'<');
-                        current_para_xml.extend_from_slice(e.name().as_ref());
-                        for attr in e.attributes().flatten() {
-                            current_para_xml.push(b' ');
-                            current_para_xml.extend_from_slice(attr.key.as_ref());
-                            current_para_xml.extend_from_slice(b"=\"");
-                            current_para_xml.extend_from_slice(&attr.value);
-                            current_para_xml.push(b'"');
-                        }
-                        current_para_xml.push(b'>');
                     }
                 },
-                Ok(Event::End(e)) => {
-                    if in_para {
-                        current_para_xml.extend_from_slice(b"</");
-                        current_para_xml.extend_from_slice(e.name().as_ref());
-                        current_para_xml.push(b'>');
-
-                        depth -= 1;
-                        if depth == 0 && e.local_name().as_ref() == b"p" {
-                            // Clone bytes and clear buffer (preserves capacity for next paragraph)
-                            let para_xml = current_para_xml.clone();
-                            current_para_xml.clear();
-                            paragraphs.push(Paragraph::new(para_xml));
-                            in_para = false;
-                        }
+                Ok(Event::Empty(e)) => {
+                    if open.is_none() && depth >= 1 && e.local_name().as_ref() == b"p" {
+                        let end = reader.buffer_position() as usize;
+                        blocks.push(CellBlock::Paragraph(Paragraph::new(
+                            element_bytes(xml, before, end).to_vec(),
+                        )));
                     }
                 },
-                Ok(Event::Text(e)) if in_para => {
-                    current_para_xml.extend_from_slice(e.as_ref());
-                },
-                Ok(Event::Empty(e)) if in_para => {
-                    current_para_xml.push(b'<');
-                    current_para_xml.extend_from_slice(e.name().as_ref());
-                    for attr in e.attributes().flatten() {
-                        current_para_xml.push(b' ');
-                        current_para_xml.extend_from_slice(attr.key.as_ref());
-                        current_para_xml.extend_from_slice(b"=\"");
-                        current_para_xml.extend_from_slice(&attr.value);
-                        current_para_xml.push(b'"');
+                Ok(Event::End(_)) => {
+                    if let Some((start, open_depth, is_table)) = open
+                        && open_depth == depth
+                    {
+                        let bytes = element_bytes(xml, start, reader.buffer_position() as usize);
+                        blocks.push(if is_table {
+                            CellBlock::Table(Box::new(Table::new(bytes.to_vec())))
+                        } else {
+                            CellBlock::Paragraph(Paragraph::new(bytes.to_vec()))
+                        });
+                        open = None;
                     }
-                    current_para_xml.extend_from_slice(b"/>");
+                    depth = depth.saturating_sub(1);
                 },
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(OoxmlError::Xml(e.to_string())),
@@ -705,10 +889,66 @@ impl Cell {
             }
         }
 
-        Ok(paragraphs)
+        Ok(blocks)
     }
 }
 
+/// A paragraph or nested table directly in a cell.
+enum CellBlock {
+    Paragraph(Paragraph),
+    Table(Box<Table>),
+}
+
+/// Slice an element out of its XML, dropping whitespace read before its tag.
+fn element_bytes(xml: &[u8], start: usize, end: usize) -> &[u8] {
+    let bytes = &xml[start..end];
+    let tag = bytes.iter().position(|&b| b == b'<').unwrap_or(0);
+    &bytes[tag..]
+}
+
+/// Parse a hex color attribute, where `auto` means no explicit color.
+fn parse_color(value: Option<String>) -> Option<RGBColor> {
+    value
+        .filter(|v| v != "auto")
+        .and_then(|v| RGBColor::from_hex(&v).ok())
+}
+
+/// Parse the background color of a `<w:shd>` element.
+///
+/// A solid pattern paints the foreground color over the whole cell.
+fn parse_shading(e: &quick_xml::events::BytesStart) -> Option<RGBColor> {
+    if attr(e, b"val").as_deref() == Some("solid") {
+        parse_color(attr(e, b"color"))
+    } else {
+        parse_color(attr(e, b"fill"))
+    }
+}
+
+/// Parse one edge of `<w:tcBorders>`.
+fn parse_border(e: &quick_xml::events::BytesStart) -> Option<CellBorder> {
+    let style = match attr(e, b"val")?.as_str() {
+        "nil" | "none" => return None,
+        "single" => BorderLineStyle::Single,
+        "thick" => BorderLineStyle::Thick,
+        "double" => BorderLineStyle::Double,
+        "triple" => BorderLineStyle::Triple,
+        "dotted" => BorderLineStyle::Dotted,
+        "dashed" => BorderLineStyle::Dashed,
+        "dotDash" => BorderLineStyle::DotDash,
+        "dotDotDash" => BorderLineStyle::DotDotDash,
+        _ => BorderLineStyle::Other,
+    };
+    // Border widths are in eighths of a point
+    let eighths = attr(e, b"sz")
+        .and_then(|v| v.parse::<f64>().ok())
+        .unwrap_or(0.0);
+    Some(CellBorder {
+        style,
+        width: Length::from_points(eighths / 8.0),
+        color: parse_color(attr(e, b"color")),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -723,4 +963,122 @@ mod tests {
         let text = cell.text().unwrap();
         assert_eq!(text, "Cell text");
     }
+
+    #[test]
+    fn test_cell_properties() {
+        let xml = br#"<w:tc xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:tcPr>
+                <w:gridSpan w:val="3"/>
+                <w:vMerge w:val="restart"/>
+                <w:tcBorders>
+                    <w:top w:val="dashed" w:sz="8" w:color="0000FF"/>
+                    <w:end w:val="single" w:sz="4" w:color="auto"/>
+                    <w:bottom w:val="none"/>
+                </w:tcBorders>
+                <w:shd w:val="clear" w:color="auto" w:fill="FFFF00"/>
+                <w:vAlign w:val="center"/>
+            </w:tcPr>
+            <w:p><w:r><w:t>Merged</w:t></w:r></w:p>
+        </w:tc>"#;
+
+        let props = Cell::new(xml.to_vec()).properties().unwrap();
+        assert_eq!(props.grid_span, 3);
+        assert_eq!(props.v_merge, Some(VMergeState::Restart));
+        assert_eq!(
+            props.vertical_alignment,
+            Some(CellVerticalAlignment::Center)
+        );
+        assert_eq!(props.shading, Some(RGBColor::new(0xFF, 0xFF, 0)));
+        let top = props.borders.top.unwrap();
+        assert_eq!(top.style, BorderLineStyle::Dashed);
+        assert_eq!(top.width.points(), 1.0);
+        assert_eq!(top.color, Some(RGBColor::new(0, 0, 0xFF)));
+        assert_eq!(props.borders.right.unwrap().color, None);
+        assert_eq!(props.borders.bottom, None);
+        assert_eq!(props.borders.left, None);
+    }
+
+    #[test]
+    fn test_nested_cell_properties_ignored() {
+        let xml = br#"<w:tc xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+            <w:tbl><w:tr><w:tc>
+                <w:tcPr><w:gridSpan w:val="2"/><w:vMerge/></w:tcPr>
+                <w:p/>
+            </w:tc></w:tr></w:tbl>
+            <w:p/>
+        </w:tc>"#;
+
+        let cell = Cell::new(xml.to_vec());
+        assert_eq!(cell.grid_span().unwrap(), 1);
+        assert_eq!(cell.v_merge().unwrap(), None);
+    }
+
+    #[test]
+    fn test_grid_before_and_column_widths() {
+        let row = Row::new(
+            br#"<w:tr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+                <w:trPr><w:gridBefore w:val="2"/></w:trPr>
+                <w:tc><w:p/></w:tc>
+            </w:tr>"#
+                .to_vec(),
+        );
+        assert_eq!(row.grid_before().unwrap(), 2);
+
+        let table = Table::new(
+            br#"<w:tbl xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+                <w:tblGrid><w:gridCol w:w="1000"/><w:gridCol w:w="2500"/></w:tblGrid>
+                <w:tr><w:tc>
+                    <w:tbl><w:tblGrid><w:gridCol w:w="300"/></w:tblGrid></w:tbl>
+                    <w:p/>
+                </w:tc></w:tr>
+            </w:tbl>"#
+                .to_vec(),
+        );
+        let widths: Vec<i64> = table
+            .column_widths()
+            .unwrap()
+            .iter()
+            .map(|w| w.twips())
+            .collect();
+        assert_eq!(widths, vec![1000, 2500]);
+    }
+
+    #[test]
+    fn test_nested_table_structure() {
+        let table = Table::new(
+            br#"<w:tbl xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+                <w:tr>
+                    <w:tc>
+                        <w:p><w:r><w:t>Before</w:t></w:r></w:p>
+                        <w:tbl>
+                            <w:tr><w:tc><w:p><w:r><w:t>A</w:t></w:r></w:p></w:tc><w:tc><w:p/></w:tc></w:tr>
+                            <w:tr><w:tc><w:p><w:r><w:t>B</w:t></w:r></w:p></w:tc><w:tc><w:p/></w:tc></w:tr>
+                        </w:tbl>
+                        <w:p/>
+                    </w:tc>
+                    <w:tc><w:p><w:r><w:t>Right</w:t></w:r></w:p></w:tc>
+                </w:tr>
+            </w:tbl>"#
+                .to_vec(),
+        );
+        assert_eq!(table.row_count().unwrap(), 1);
+
+        let row = &table.rows().unwrap()[0];
+        assert_eq!(row.cell_count().unwrap(), 2);
+
+        let cell = &row.cells().unwrap()[0];
+        assert_eq!(cell.paragraphs().unwrap().len(), 2);
+        assert_eq!(cell.paragraphs().unwrap()[0].text().unwrap(), "Before");
+        let nested = cell.tables().unwrap();
+        assert_eq!(nested.len(), 1);
+        assert_eq!(nested[0].row_count().unwrap(), 2);
+        assert_eq!(
+            nested[0].rows().unwrap()[1].cells().unwrap()[0]
+                .text()
+                .unwrap(),
+            "B"
+        );
+        // The cell text still covers the nested table
+        assert_eq!(cell.text().unwrap(), "BeforeAB");
+    }
 }
@@ -0,0 +1,152 @@
+/// Text box support for DOCX documents.
+///
+/// Text boxes and shapes with text keep their content in a `<w:txbxContent>`
+/// element inside the run that anchors them. DrawingML shapes store it in
+/// `<wps:wsp>/<wps:txbx>`, and VML shapes (older documents, and the
+/// `<mc:Fallback>` written next to every DrawingML shape) in `<v:textbox>`.
+use crate::ooxml::docx::paragraph::Paragraph;
+use crate::ooxml::docx::parts::document_part::parse_paragraphs;
+use crate::ooxml::error::{OoxmlError, Result};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+/// A text box or shape with text, anchored in a paragraph.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::ooxml::docx::Package;
+///
+/// let pkg = Package::open("document.docx")?;
+/// let doc = pkg.document()?;
+///
+/// for para in doc.paragraphs()? {
+///     for text_box in para.text_boxes()? {
+///         println!("{}: {}", text_box.name(), text_box.text()?);
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TextBox {
+    /// Shape name from `wp:docPr` (empty for VML shapes)
+    name: String,
+    /// Paragraphs of the text box content
+    paragraphs: Vec<Paragraph>,
+}
+
+impl TextBox {
+    /// Get the shape name, such as "Text Box 1".
+    ///
+    /// VML text boxes have no name and return an empty string.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the paragraphs of the text box, including those in its tables.
+    #[inline]
+    pub fn paragraphs(&self) -> &[Paragraph] {
+        &self.paragraphs
+    }
+
+    /// Get the text of the text box, with paragraphs separated by newlines.
+    pub fn text(&self) -> Result<String> {
+        let texts = self
+            .paragraphs
+            .iter()
+            .map(Paragraph::text)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(texts.join("\n"))
+    }
+}
+
+/// Parse the text boxes anchored in paragraph XML, in document order.
+///
+/// A DrawingML shape is written with a VML copy in `<mc:Fallback>`, so
+/// fallback content is skipped to report each text box once. Text boxes
+/// nested in another text box belong to the paragraphs of the outer one.
+pub(crate) fn parse_text_boxes(xml_bytes: &[u8]) -> Result<Vec<TextBox>> {
+    let mut reader = Reader::from_reader(xml_bytes);
+    reader.config_mut().trim_text(true);
+
+    let mut text_boxes = Vec::new();
+    let mut name = String::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"Fallback" => {
+                    reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                },
+                b"drawing" | b"pict" => name.clear(),
+                b"docPr" => name = shape_name(&e),
+                b"txbxContent" => {
+                    let span = reader
+                        .read_to_end(e.name())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    let content = &xml_bytes[span.start as usize..span.end as usize];
+
+                    let mut paragraphs = Vec::new();
+                    parse_paragraphs(content, |para| paragraphs.push(para))
+                        .map_err(|(_, e)| OoxmlError::Xml(e.to_string()))?;
+                    text_boxes.push(TextBox {
+                        name: std::mem::take(&mut name),
+                        paragraphs,
+                    });
+                },
+                _ => {},
+            },
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"docPr" => name = shape_name(&e),
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+            _ => {},
+        }
+    }
+
+    Ok(text_boxes)
+}
+
+/// Read the `name` attribute of a `wp:docPr` element.
+fn shape_name(e: &BytesStart<'_>) -> String {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == b"name")
+        .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WPS_WITH_FALLBACK: &str = r#"<w:p><w:r><w:t>Body</w:t></w:r><w:r><mc:AlternateContent><mc:Choice Requires="wps"><w:drawing><wp:anchor><wp:docPr id="1" name="Text Box 1"/><a:graphic><a:graphicData><wps:wsp><wps:txbx><w:txbxContent><w:p><w:r><w:t>First</w:t></w:r></w:p><w:p><w:r><w:t>Second</w:t></w:r></w:p></w:txbxContent></wps:txbx></wps:wsp></a:graphicData></a:graphic></wp:anchor></w:drawing></mc:Choice><mc:Fallback><w:pict><v:shape><v:textbox><w:txbxContent><w:p><w:r><w:t>First</w:t></w:r></w:p><w:p><w:r><w:t>Second</w:t></w:r></w:p></w:txbxContent></v:textbox></v:shape></w:pict></mc:Fallback></mc:AlternateContent></w:r></w:p>"#;
+
+    #[test]
+    fn test_parse_drawingml_text_box_skips_fallback() {
+        let text_boxes = parse_text_boxes(WPS_WITH_FALLBACK.as_bytes()).unwrap();
+        assert_eq!(text_boxes.len(), 1);
+        assert_eq!(text_boxes[0].name(), "Text Box 1");
+        assert_eq!(text_boxes[0].paragraphs().len(), 2);
+        assert_eq!(text_boxes[0].text().unwrap(), "First\nSecond");
+    }
+
+    #[test]
+    fn test_parse_vml_text_box() {
+        let xml = r#"<w:p><w:r><w:pict><v:shape><v:textbox><w:txbxContent><w:p><w:r><w:t>Callout</w:t></w:r></w:p></w:txbxContent></v:textbox></v:shape></w:pict></w:r></w:p>"#;
+        let text_boxes = parse_text_boxes(xml.as_bytes()).unwrap();
+        assert_eq!(text_boxes.len(), 1);
+        assert_eq!(text_boxes[0].name(), "");
+        assert_eq!(text_boxes[0].text().unwrap(), "Callout");
+    }
+
+    #[test]
+    fn test_text_box_content_not_in_paragraph_text() {
+        let para = Paragraph::new(WPS_WITH_FALLBACK.as_bytes().to_vec());
+        assert_eq!(para.text().unwrap(), "Body");
+        let runs = para.runs().unwrap();
+        assert_eq!(runs[1].text().unwrap(), "");
+    }
+}
@@ -1,6 +1,7 @@
 /// Theme support for Word documents.
 ///
 /// Themes define the color scheme, fonts, and effects used in a document.
+use crate::ooxml::drawings::color::ColorScheme;
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::part::Part;
 use quick_xml::Reader;
@@ -33,6 +34,8 @@ pub struct Theme {
     minor_font: Option<String>,
     /// Color scheme name
     color_scheme: Option<String>,
+    /// Color scheme colors
+    colors: ColorScheme,
 }
 
 impl Theme {
@@ -43,6 +46,7 @@ impl Theme {
             major_font: None,
             minor_font: None,
             color_scheme: None,
+            colors: ColorScheme::default(),
         }
     }
 
@@ -70,13 +74,22 @@ impl Theme {
         self.color_scheme.as_deref()
     }
 
+    /// Get the colors of the color scheme, used to resolve theme colors.
+    ///
+    /// Slots the theme does not define keep the default Office colors.
+    #[inline]
+    pub fn colors(&self) -> &ColorScheme {
+        &self.colors
+    }
+
     /// Extract theme from a theme part.
     pub(crate) fn extract_from_part(part: &dyn Part) -> Result<Self> {
-        let xml_bytes = part.blob();
+        let xml_bytes = part.try_blob()?;
         let mut reader = Reader::from_reader(xml_bytes);
         reader.config_mut().trim_text(true);
 
         let mut theme = Self::new();
+        theme.colors = ColorScheme::from_theme_xml(xml_bytes)?;
         let mut in_major_font = false;
         let mut in_minor_font = false;
 
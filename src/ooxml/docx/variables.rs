@@ -80,7 +80,7 @@ impl DocumentVariables {
     ///
     /// Variables are stored in the `<w:docVars>` section of settings.
     pub(crate) fn extract_from_settings_part(part: &dyn Part) -> Result<Self> {
-        let xml_bytes = part.blob();
+        let xml_bytes = part.try_blob()?;
         let mut reader = Reader::from_reader(xml_bytes);
         reader.config_mut().trim_text(true);
 
@@ -5,6 +5,7 @@ use crate::common::xml::escape_xml;
 /// drop-down lists, and other content types. They're commonly used in templates
 /// and forms.
 use crate::ooxml::error::Result;
+use chrono::NaiveDate;
 use std::fmt::Write as FmtWrite;
 
 /// A mutable content control in a Word document.
@@ -302,6 +303,47 @@ impl MutableContentControl {
     }
 }
 
+/// A value to put into an existing content control.
+///
+/// Used with [`MutableDocument::set_content_control_value`](super::MutableDocument::set_content_control_value)
+/// to fill in forms. Strings convert to [`Text`](Self::Text), booleans to
+/// [`Checked`](Self::Checked) and dates to [`Date`](Self::Date).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContentControlValue {
+    /// Text for plain text, rich text and combo box controls
+    Text(String),
+    /// State of a checkbox
+    Checked(bool),
+    /// Date for a date picker
+    Date(NaiveDate),
+    /// Entry of a drop-down list or combo box, by value or display text
+    Item(String),
+}
+
+impl From<&str> for ContentControlValue {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+impl From<String> for ContentControlValue {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<bool> for ContentControlValue {
+    fn from(value: bool) -> Self {
+        Self::Checked(value)
+    }
+}
+
+impl From<NaiveDate> for ContentControlValue {
+    fn from(value: NaiveDate) -> Self {
+        Self::Date(value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
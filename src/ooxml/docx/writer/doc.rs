@@ -1,19 +1,33 @@
 /// Document writer implementation for DOCX.
 use crate::ooxml::error::{OoxmlError, Result};
+use std::borrow::Borrow;
+use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
+use std::hash::{BuildHasher, Hash};
 
 // Import shared format types
 pub use super::super::format::ImageFormat;
 // Import from other writer modules
 use super::comment::MutableComment;
+use super::content_control::ContentControlValue;
+use super::form;
+use super::fragment::{FragmentInsertion, FragmentPosition};
+use super::merge::{FieldContext, FieldOperation};
 use super::note::Note;
 use super::paragraph::{MutableParagraph, ParagraphElement};
+use super::relmap::RelationshipMapper;
+use super::replace::{self, ReplaceOptions};
 use super::section::SectionProperties;
+use super::style::MutableStyle;
 use super::table::MutableTable;
 use super::theme::MutableTheme;
 use super::toc::TableOfContents;
 use super::watermark::Watermark;
 // Import settings types
+use super::super::bookmark::Bookmark;
+use super::super::content_control::ContentControl;
+use super::super::enums::WdHeaderFooter;
+use super::super::fragment::DocumentFragment;
 use super::super::settings::ProtectionType;
 
 /// A mutable Word document for writing and modification.
@@ -23,10 +37,10 @@ use super::super::settings::ProtectionType;
 pub struct MutableDocument {
     /// Document body content (paragraphs, tables, etc.)
     body: DocumentBody,
-    /// Header content (optional)
-    header: Option<Vec<MutableParagraph>>,
-    /// Footer content (optional)
-    footer: Option<Vec<MutableParagraph>>,
+    /// Header content by kind (primary, first page, even pages)
+    headers: Vec<(WdHeaderFooter, Vec<MutableParagraph>)>,
+    /// Footer content by kind (primary, first page, even pages)
+    footers: Vec<(WdHeaderFooter, Vec<MutableParagraph>)>,
     /// Footnotes (ID -> Note)
     footnotes: Vec<Note>,
     /// Endnotes (ID -> Note)
@@ -43,6 +57,20 @@ pub struct MutableDocument {
     pub(crate) watermark: Option<Watermark>,
     /// Table of Contents configuration (optional)
     toc_config: Option<(usize, TableOfContents)>, // (insertion index, config)
+    /// Whether Word should update fields when the document is opened
+    update_fields: bool,
+    /// Content controls of the loaded document
+    content_controls: Vec<ContentControl>,
+    /// Values to fill into content controls when saving (tag, value)
+    content_control_values: Vec<(String, ContentControlValue)>,
+    /// Field updates and flattening to apply when saving, in order
+    field_operations: Vec<FieldOperation>,
+    /// Bookmark names of the loaded document
+    bookmarks: Vec<String>,
+    /// Fragments to insert at bookmarks when saving, in order
+    fragment_insertions: Vec<FragmentInsertion>,
+    /// Styles to add to the styles part when saving
+    styles: Vec<MutableStyle>,
     /// Whether the document has been modified
     modified: bool,
 }
@@ -62,8 +90,6 @@ pub struct DocumentProtection {
 use crate::fonts::CollectGlyphs;
 #[cfg(feature = "fonts")]
 use roaring::RoaringBitmap;
-#[cfg(feature = "fonts")]
-use std::collections::HashMap;
 
 #[cfg(feature = "fonts")]
 impl CollectGlyphs for MutableDocument {
@@ -75,24 +101,16 @@ impl CollectGlyphs for MutableDocument {
             let element_glyphs = match element {
                 BodyElement::Paragraph(p) => p.collect_glyphs(),
                 BodyElement::Table(t) => t.collect_glyphs(),
+                BodyElement::SectionBreak(_) => continue,
             };
             for (font, bitmap) in element_glyphs {
                 *glyphs.entry(font).or_insert_with(RoaringBitmap::new) |= bitmap;
             }
         }
 
-        // Collect from headers
-        if let Some(headers) = &self.header {
-            for p in headers {
-                for (font, bitmap) in p.collect_glyphs() {
-                    *glyphs.entry(font).or_insert_with(RoaringBitmap::new) |= bitmap;
-                }
-            }
-        }
-
-        // Collect from footers
-        if let Some(footers) = &self.footer {
-            for p in footers {
+        // Collect from headers and footers
+        for (_, paragraphs) in self.headers.iter().chain(&self.footers) {
+            for p in paragraphs {
                 for (font, bitmap) in p.collect_glyphs() {
                     *glyphs.entry(font).or_insert_with(RoaringBitmap::new) |= bitmap;
                 }
@@ -152,16 +170,23 @@ impl MutableDocument {
     pub fn new() -> Self {
         Self {
             body: DocumentBody::new(),
-            header: None,
-            footer: None,
+            headers: Vec::new(),
+            footers: Vec::new(),
             footnotes: Vec::new(),
             endnotes: Vec::new(),
             comments: Vec::new(),
             protection: None,
             toc_config: None,
+            update_fields: false,
             section: SectionProperties::default(),
             theme: None,
             watermark: None,
+            content_controls: Vec::new(),
+            content_control_values: Vec::new(),
+            field_operations: Vec::new(),
+            bookmarks: Vec::new(),
+            fragment_insertions: Vec::new(),
+            styles: Vec::new(),
             modified: false,
         }
     }
@@ -169,11 +194,17 @@ impl MutableDocument {
     /// Create a mutable document from existing XML content.
     pub fn from_xml(xml: &str) -> Result<Self> {
         let body = DocumentBody::from_xml(xml)?;
+        let content_controls = ContentControl::extract_from_document(xml.as_bytes())?;
+        let bookmarks = Bookmark::extract_from_document(xml.as_bytes())?
+            .into_iter()
+            .map(|bookmark| bookmark.name().to_string())
+            .collect();
         Ok(Self {
             body,
             toc_config: None,
-            header: None,
-            footer: None,
+            update_fields: false,
+            headers: Vec::new(),
+            footers: Vec::new(),
             footnotes: Vec::new(),
             endnotes: Vec::new(),
             comments: Vec::new(),
@@ -181,21 +212,61 @@ impl MutableDocument {
             section: SectionProperties::default(),
             theme: None,
             watermark: None,
+            content_controls,
+            content_control_values: Vec::new(),
+            field_operations: Vec::new(),
+            bookmarks,
+            fragment_insertions: Vec::new(),
+            styles: Vec::new(),
             modified: false,
         })
     }
 
     /// Get a mutable reference to the section properties.
+    ///
+    /// These are the properties of the last section, the one following the
+    /// latest [section break](Self::insert_section_break).
     pub fn section_mut(&mut self) -> &mut SectionProperties {
         self.modified = true;
         &mut self.section
     }
 
     /// Get a reference to the section properties.
+    ///
+    /// See [`section_mut`](Self::section_mut).
     pub fn section(&self) -> &SectionProperties {
         &self.section
     }
 
+    /// End the current section and start a new one with the given properties.
+    ///
+    /// The content added so far keeps the current section properties, and
+    /// the content added afterwards is laid out with `properties` (page size,
+    /// orientation, margins, columns and page numbering). Headers and footers
+    /// apply to every section.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::ooxml::docx::writer::{MutableDocument, SectionProperties};
+    ///
+    /// let mut doc = MutableDocument::new();
+    /// doc.add_paragraph_with_text("Introduction");
+    ///
+    /// // A landscape section for a wide table
+    /// doc.insert_section_break(SectionProperties::letter().landscape());
+    /// doc.add_table(2, 12);
+    ///
+    /// // Back to portrait, in two columns
+    /// doc.insert_section_break(SectionProperties::letter().columns(2, 0.5));
+    /// doc.add_paragraph_with_text("Appendix");
+    /// ```
+    pub fn insert_section_break(&mut self, properties: SectionProperties) {
+        let previous = std::mem::replace(&mut self.section, properties);
+        self.body.elements.push(BodyElement::SectionBreak(previous));
+        self.modified = true;
+    }
+
     /// Add a new paragraph to the end of the document.
     pub fn add_paragraph(&mut self) -> &mut MutableParagraph {
         self.modified = true;
@@ -219,7 +290,7 @@ impl MutableDocument {
         let style = if level == 0 {
             "Title".to_string()
         } else {
-            format!("Heading {}", level)
+            format!("Heading{}", level)
         };
         let para = self.add_paragraph();
         para.set_style(&style);
@@ -255,54 +326,149 @@ impl MutableDocument {
         self.modified
     }
 
+    /// Get the content controls of the loaded document.
+    ///
+    /// Values set with [`set_content_control_value`](Self::set_content_control_value)
+    /// are not reflected here; they are written when the package is saved.
+    pub fn content_controls(&self) -> &[ContentControl] {
+        &self.content_controls
+    }
+
+    /// Fill in the content controls tagged `tag` with `value`.
+    ///
+    /// The value has to suit the control: checkboxes take a `bool`, date
+    /// pickers a [`NaiveDate`](chrono::NaiveDate), and drop-down lists one of
+    /// their entries, by value or display text. The document XML is updated
+    /// when the package is saved, together with the custom XML nodes of
+    /// data-bound controls.
+    ///
+    /// Returns the number of controls with the tag.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    ///
+    /// let mut pkg = Package::open("form.docx")?;
+    /// let doc = pkg.document_mut()?;
+    /// doc.set_content_control_value("name", "Ada Lovelace")?;
+    /// doc.set_content_control_value("subscribe", true)?;
+    /// pkg.save("filled.docx")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn set_content_control_value(
+        &mut self,
+        tag: &str,
+        value: impl Into<ContentControlValue>,
+    ) -> Result<usize> {
+        let value = value.into();
+        let mut count = 0;
+        for control in self
+            .content_controls
+            .iter()
+            .filter(|c| c.tag() == Some(tag))
+        {
+            form::resolve(control, &value)?;
+            count += 1;
+        }
+        if count > 0 {
+            self.content_control_values.push((tag.to_string(), value));
+        }
+        Ok(count)
+    }
+
+    /// Get the content control values waiting to be saved.
+    pub(crate) fn content_control_values(&self) -> &[(String, ContentControlValue)] {
+        &self.content_control_values
+    }
+
     /// Get or create the header.
     pub fn header(&mut self) -> &mut Vec<MutableParagraph> {
-        if self.header.is_none() {
-            self.header = Some(Vec::new());
-            self.modified = true;
-        }
-        self.header.as_mut().unwrap()
+        self.modified = true;
+        header_footer_slot(&mut self.headers, WdHeaderFooter::Primary)
     }
 
     /// Get or create the footer.
     pub fn footer(&mut self) -> &mut Vec<MutableParagraph> {
-        if self.footer.is_none() {
-            self.footer = Some(Vec::new());
-            self.modified = true;
-        }
-        self.footer.as_mut().unwrap()
+        self.modified = true;
+        header_footer_slot(&mut self.footers, WdHeaderFooter::Primary)
     }
 
     /// Check if the document has a header.
     pub fn has_header(&self) -> bool {
-        self.header.is_some()
+        self.has_header_of(WdHeaderFooter::Primary)
     }
 
     /// Check if the document has a footer.
     pub fn has_footer(&self) -> bool {
-        self.footer.is_some()
+        self.has_footer_of(WdHeaderFooter::Primary)
+    }
+
+    /// Check if the document has a header of the given kind.
+    pub fn has_header_of(&self, kind: WdHeaderFooter) -> bool {
+        self.headers.iter().any(|(k, _)| *k == kind)
+    }
+
+    /// Check if the document has a footer of the given kind.
+    pub fn has_footer_of(&self, kind: WdHeaderFooter) -> bool {
+        self.footers.iter().any(|(k, _)| *k == kind)
     }
 
     /// Add a header to the document.
     pub fn add_header_paragraph(&mut self) -> &mut MutableParagraph {
-        if self.header.is_none() {
-            self.header = Some(Vec::new());
-        }
-        let para = MutableParagraph::new();
-        self.header.as_mut().unwrap().push(para);
-        self.modified = true;
-        self.header.as_mut().unwrap().last_mut().unwrap()
+        self.header().push(MutableParagraph::new());
+        self.header().last_mut().unwrap()
     }
 
     /// Add a footer to the document.
     pub fn add_footer_paragraph(&mut self) -> &mut MutableParagraph {
-        if self.footer.is_none() {
-            self.footer = Some(Vec::new());
-        }
-        let para = MutableParagraph::new();
-        self.footer.as_mut().unwrap().push(para);
+        self.footer().push(MutableParagraph::new());
+        self.footer().last_mut().unwrap()
+    }
+
+    /// Set the header of the given kind, replacing any previous content.
+    ///
+    /// A first-page header turns on the different first page of the
+    /// section, and an even-page header the different odd and even pages
+    /// of the document.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::ooxml::docx::WdHeaderFooter;
+    /// use litchi::ooxml::docx::writer::{MutableDocument, MutableParagraph, PageNumberFormat};
+    ///
+    /// let mut doc = MutableDocument::new();
+    /// let mut letterhead = MutableParagraph::new();
+    /// letterhead.add_run_with_text("ACME Corporation").bold(true);
+    /// doc.set_header(WdHeaderFooter::FirstPage, vec![letterhead]);
+    ///
+    /// let mut page = MutableParagraph::new();
+    /// page.add_page_x_of_y(PageNumberFormat::Decimal);
+    /// doc.set_footer(WdHeaderFooter::Primary, vec![page]);
+    /// ```
+    pub fn set_header(&mut self, kind: WdHeaderFooter, paragraphs: Vec<MutableParagraph>) {
         self.modified = true;
-        self.footer.as_mut().unwrap().last_mut().unwrap()
+        *header_footer_slot(&mut self.headers, kind) = paragraphs;
+    }
+
+    /// Set the footer of the given kind, replacing any previous content.
+    ///
+    /// See [`set_header`](Self::set_header).
+    pub fn set_footer(&mut self, kind: WdHeaderFooter, paragraphs: Vec<MutableParagraph>) {
+        self.modified = true;
+        *header_footer_slot(&mut self.footers, kind) = paragraphs;
+    }
+
+    /// Check whether the first page has its own header or footer.
+    pub(crate) fn has_title_page(&self) -> bool {
+        self.has_header_of(WdHeaderFooter::FirstPage)
+            || self.has_footer_of(WdHeaderFooter::FirstPage)
+    }
+
+    /// Check whether even pages have their own header or footer.
+    pub(crate) fn has_even_and_odd_headers(&self) -> bool {
+        self.has_header_of(WdHeaderFooter::EvenPage) || self.has_footer_of(WdHeaderFooter::EvenPage)
     }
 
     /// Add a footnote and return its ID and mutable reference.
@@ -494,6 +660,12 @@ impl MutableDocument {
 
     /// Add a table of contents at the current position in the document.
     ///
+    /// The entries are generated from the document's heading paragraphs when
+    /// the document is saved, so headings added afterwards are included as
+    /// well. Each listed heading is bookmarked and linked from its entry, and
+    /// the document is flagged to update its fields on open so that Word
+    /// fills in the page numbers.
+    ///
     /// # Arguments
     ///
     /// * `toc` - Table of contents configuration
@@ -506,9 +678,10 @@ impl MutableDocument {
     /// let toc = TableOfContents::new()
     ///     .heading_levels(1, 3)
     ///     .title("Contents");
-    /// doc.add_toc(toc);
+    /// doc.add_table_of_contents(toc)?;
+    /// doc.add_heading("Introduction", 1)?;
     /// ```
-    pub fn add_toc(&mut self, toc: TableOfContents) -> Result<()> {
+    pub fn add_table_of_contents(&mut self, toc: TableOfContents) -> Result<()> {
         // Add optional title paragraph with TOCHeading style
         if let Some(title) = toc.get_title() {
             let title_para = self.add_paragraph();
@@ -522,16 +695,179 @@ impl MutableDocument {
 
         // Store the TOC configuration for later generation (at save time)
         self.toc_config = Some((insertion_index, toc));
+        self.update_fields = true;
+
+        self.modified = true;
+        Ok(())
+    }
+
+    /// Add a table of contents at the current position in the document.
+    ///
+    /// Alias of [`add_table_of_contents`](Self::add_table_of_contents).
+    pub fn add_toc(&mut self, toc: TableOfContents) -> Result<()> {
+        self.add_table_of_contents(toc)
+    }
 
+    /// Set whether Word should update fields when the document is opened.
+    ///
+    /// Enabled automatically by [`add_table_of_contents`](Self::add_table_of_contents).
+    pub fn set_update_fields_on_open(&mut self, enabled: bool) {
+        self.update_fields = enabled;
         self.modified = true;
+    }
+
+    /// Compute fresh results for the fields of the document.
+    ///
+    /// `MERGEFIELD`, `DOCPROPERTY`, `DATE`, `TIME`, `SEQ` and `IF` fields,
+    /// nested ones included, get results computed from `context`; other
+    /// fields keep their current results. See [`merge`](super::merge) for the
+    /// supported switches.
+    ///
+    /// Fields in the document, its headers, footers, footnotes and endnotes
+    /// are updated when the package is saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    /// use litchi::ooxml::docx::writer::FieldContext;
+    ///
+    /// let mut pkg = Package::open("letter_template.docx")?;
+    /// let context = FieldContext::new()
+    ///     .with_merge_field("FirstName", "Ada")
+    ///     .with_merge_field("City", "London");
+    /// pkg.document_mut()?.update_fields(context);
+    /// pkg.save("letter.docx")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn update_fields(&mut self, context: FieldContext) {
+        self.field_operations.push(FieldOperation::Update(context));
+    }
+
+    /// Replace every field with its current result, for archival output.
+    ///
+    /// Applied after any earlier [`update_fields`](Self::update_fields) when
+    /// the package is saved; the result runs keep their formatting.
+    pub fn flatten_fields(&mut self) {
+        self.field_operations.push(FieldOperation::Flatten);
+    }
+
+    /// Get the field operations waiting to be saved.
+    pub(crate) fn field_operations(&self) -> &[FieldOperation] {
+        &self.field_operations
+    }
+
+    /// Insert content copied from another document at the bookmark `name`.
+    ///
+    /// Styles of the fragment are matched to destination styles by name,
+    /// with the destination definitions winning unless the fragment was
+    /// made with [`with_source_styles`](DocumentFragment::with_source_styles);
+    /// missing styles are added. Numbering definitions, relationships and
+    /// bookmark IDs are renumbered, and images and other parts are copied
+    /// along. The insertion happens when the package is saved.
+    ///
+    /// Block fragments inserted inside a paragraph split it in two.
+    ///
+    /// Returns an error if the document has no bookmark called `name`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::docx::Package;
+    /// use litchi::ooxml::docx::writer::FragmentPosition;
+    ///
+    /// let source = Package::open("boilerplate.docx")?;
+    /// let fragment = source.document()?.extract_bookmark_content("warranty")?;
+    ///
+    /// let mut pkg = Package::open("offer.docx")?;
+    /// pkg.document_mut()?
+    ///     .insert_fragment_at_bookmark("terms", fragment, FragmentPosition::End)?;
+    /// pkg.save("offer_out.docx")?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn insert_fragment_at_bookmark(
+        &mut self,
+        name: &str,
+        fragment: DocumentFragment,
+        position: FragmentPosition,
+    ) -> Result<()> {
+        if !self.bookmarks.iter().any(|bookmark| bookmark == name) && !self.has_bookmark(name) {
+            return Err(OoxmlError::InvalidFormat(format!(
+                "Bookmark '{}' not found",
+                name
+            )));
+        }
+        self.fragment_insertions.push(FragmentInsertion {
+            bookmark: name.to_string(),
+            fragment,
+            position,
+        });
         Ok(())
     }
 
+    /// Check whether a paragraph added to the document starts the bookmark `name`.
+    fn has_bookmark(&self, name: &str) -> bool {
+        self.body.elements.iter().any(|element| match element {
+            BodyElement::Paragraph(paragraph) => paragraph.elements.iter().any(
+                |element| matches!(element, ParagraphElement::BookmarkStart(b) if b.name() == name),
+            ),
+            BodyElement::Table(_) | BodyElement::SectionBreak(_) => false,
+        })
+    }
+
+    /// Get the fragment insertions waiting to be saved.
+    pub(crate) fn fragment_insertions(&self) -> &[FragmentInsertion] {
+        &self.fragment_insertions
+    }
+
+    /// Add a named style to the document, replacing any style with the
+    /// same ID.
+    ///
+    /// Apply it with [`MutableParagraph::set_style`] or
+    /// [`MutableRun::style`](super::MutableRun::style) using its style ID.
+    /// The style is written to the styles part when the package is saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::ooxml::docx::WdStyleType;
+    /// use litchi::ooxml::docx::writer::{MutableDocument, MutableStyle};
+    ///
+    /// let mut style = MutableStyle::new("BodySmall", "Body Small", WdStyleType::Paragraph);
+    /// style.set_based_on(Some("Normal".to_string()));
+    /// style.set_next(Some("BodySmall".to_string()));
+    /// style.set_font_name(Some("Arial".to_string()));
+    /// style.set_font_size(Some(18)); // 9pt
+    /// style.set_space_after(Some(60));
+    ///
+    /// let mut doc = MutableDocument::new();
+    /// doc.add_style(style);
+    /// doc.add_paragraph_with_text("Fine print").set_style("BodySmall");
+    /// ```
+    pub fn add_style(&mut self, style: MutableStyle) {
+        self.styles.retain(|s| s.style_id() != style.style_id());
+        self.styles.push(style);
+        self.modified = true;
+    }
+
+    /// Get the styles waiting to be saved.
+    pub(crate) fn styles(&self) -> &[MutableStyle] {
+        &self.styles
+    }
+
     /// Generate and insert TOC entries.
     /// This is called automatically before serialization.
+    ///
+    /// Every heading paragraph within the configured levels receives a
+    /// `_Toc` bookmark, and one entry paragraph per heading is inserted at
+    /// the recorded position. The entries form the result of a complex
+    /// `TOC` field: the field begins in the first entry and ends in a
+    /// trailing paragraph, so Word and LibreOffice treat them as a single
+    /// updatable table of contents.
     pub(crate) fn generate_toc_if_needed(&mut self) -> Result<()> {
         use super::field::MutableField;
-        use std::fmt::Write as FmtWrite;
+        use super::hyperlink::{HyperlinkElement, MutableHyperlink};
+        use super::run::MutableRun;
 
         // Check if we have a TOC to generate
         let Some((insertion_index, toc)) = self.toc_config.take() else {
@@ -540,173 +876,164 @@ impl MutableDocument {
 
         // Step 1: Scan document for headings and add bookmarks
         let mut heading_info = Vec::new();
-        let mut bookmark_counter = 0u32;
         let start_level = toc.start_level();
         let end_level = toc.end_level();
 
-        // Iterate through all body elements to find headings
+        // Bookmark IDs must be unique, so continue after any existing bookmark
+        let mut bookmark_id = self
+            .body
+            .elements
+            .iter()
+            .filter_map(|element| match element {
+                BodyElement::Paragraph(para) => Some(para),
+                BodyElement::Table(_) | BodyElement::SectionBreak(_) => None,
+            })
+            .flat_map(|para| &para.elements)
+            .filter_map(|element| match element {
+                ParagraphElement::BookmarkStart(bookmark) => Some(bookmark.id() + 1),
+                _ => None,
+            })
+            .max()
+            .unwrap_or(0);
+
         for element in &mut self.body.elements {
             if let BodyElement::Paragraph(para) = element
-                && let Some(style) = &para.style
+                && let Some(level) = para.style.as_deref().and_then(heading_level)
+                && (start_level..=end_level).contains(&level)
             {
-                // Check if this is a heading within our TOC range
-                let heading_level = match style.as_str() {
-                    "Heading1" => Some(1),
-                    "Heading2" => Some(2),
-                    "Heading3" => Some(3),
-                    "Heading4" => Some(4),
-                    "Heading5" => Some(5),
-                    "Heading6" => Some(6),
-                    "Heading7" => Some(7),
-                    "Heading8" => Some(8),
-                    "Heading9" => Some(9),
-                    _ => None,
-                };
-
-                if let Some(level) = heading_level
-                    && level >= start_level
-                    && level <= end_level
-                {
-                    // Extract heading text
-                    let mut heading_text = String::new();
-                    for elem in &para.elements {
-                        if let super::paragraph::ParagraphElement::Run(run) = elem {
-                            heading_text.push_str(&run.get_text());
-                        }
+                // Extract heading text
+                let mut heading_text = String::new();
+                for elem in &para.elements {
+                    if let ParagraphElement::Run(run) = elem {
+                        heading_text.push_str(&run.get_text());
                     }
-
-                    // Generate unique bookmark name
-                    let bookmark_name = format!("_Toc{}", 213359267 + bookmark_counter);
-                    let bookmark_id = bookmark_counter;
-                    bookmark_counter += 1;
-
-                    // Add bookmark to the heading paragraph
-                    para.add_bookmark_start(bookmark_id, &bookmark_name);
-                    para.add_bookmark_end(bookmark_id);
-
-                    // Store heading info for TOC generation
-                    heading_info.push((level, heading_text, bookmark_name));
                 }
+
+                // Word names TOC bookmarks `_Toc` followed by a number
+                let bookmark_name = format!("_Toc{}", 213359267 + heading_info.len());
+
+                // Wrap the heading content in the bookmark
+                para.elements.insert(
+                    0,
+                    ParagraphElement::BookmarkStart(super::bookmark::MutableBookmark::new(
+                        bookmark_id,
+                        bookmark_name.clone(),
+                    )),
+                );
+                para.add_bookmark_end(bookmark_id);
+                bookmark_id += 1;
+
+                // Store heading info for TOC generation
+                heading_info.push((level, heading_text, bookmark_name));
             }
         }
 
         // Step 2: Build TOC paragraphs
+        let field_start = [
+            MutableField::begin(),
+            MutableField::instruction_char(format!(" {} ", toc.build_field_instruction())),
+            MutableField::separate(),
+        ];
         let mut toc_paragraphs = Vec::new();
 
-        // First paragraph: TOC field wrapper
-        let mut toc_field_para = MutableParagraph::new();
-        let instruction = toc.build_field_instruction();
-        toc_field_para
-            .elements
-            .push(super::paragraph::ParagraphElement::Field(
-                MutableField::begin(),
-            ));
-        toc_field_para
-            .elements
-            .push(super::paragraph::ParagraphElement::Field(
-                MutableField::instruction_char(instruction),
-            ));
-        toc_field_para
-            .elements
-            .push(super::paragraph::ParagraphElement::Field(
-                MutableField::separate(),
-            ));
-
-        toc_paragraphs.push(toc_field_para);
-
-        // Generate TOC entry paragraphs
         for (level, heading_text, bookmark_name) in heading_info {
             let mut toc_entry = MutableParagraph::new();
 
             // Set TOC style
             toc_entry.style = Some(format!("TOC{}", level));
 
-            // Set paragraph properties (tab and indent)
-            toc_entry
-                .properties
-                .tab_stops
-                .push(super::paragraph::TabStop {
-                    position: 9350,
-                    alignment: "right".to_string(),
-                    leader: Some("dot".to_string()),
-                });
-
-            let indent = match level {
-                1 => 0,
-                2 => 440,
-                3 => 880,
-                _ => (level as i32 - 1) * 440,
-            };
-            toc_entry.properties.indent_left = Some(indent);
+            // Page numbers are right-aligned on a dotted tab stop
+            if toc.has_page_numbers() && toc.has_right_aligned_page_numbers() {
+                toc_entry
+                    .properties
+                    .tab_stops
+                    .push(super::paragraph::TabStop {
+                        position: 9350,
+                        alignment: "right".to_string(),
+                        leader: Some("dot".to_string()),
+                    });
+            }
+            toc_entry.properties.indent_left = Some((level as i32 - 1) * 440);
 
-            // Add hyperlink with runs and PAGEREF field
-            let mut hyperlink =
-                super::hyperlink::MutableHyperlink::new_anchor(bookmark_name.clone());
+            // Entry content: heading text, then a tab and a PAGEREF field
+            let mut content = Vec::new();
 
-            let mut text_run = super::run::MutableRun::new();
+            let mut text_run = MutableRun::new();
             text_run.set_text(&heading_text);
             text_run.properties.no_proof = true;
-            hyperlink.add_run(text_run);
-
-            let mut tab_run = super::run::MutableRun::new();
-            tab_run.add_tab();
-            tab_run.properties.no_proof = true;
-            tab_run.properties.web_hidden = true;
-            hyperlink.add_run(tab_run);
-
-            hyperlink
-                .elements
-                .push(super::hyperlink::HyperlinkElement::Field(
-                    MutableField::begin(),
-                ));
-
-            let mut pageref_instr = String::new();
-            write!(&mut pageref_instr, " PAGEREF {} \\h ", bookmark_name).unwrap();
-            hyperlink
-                .elements
-                .push(super::hyperlink::HyperlinkElement::Field(
-                    MutableField::instruction_char(pageref_instr),
-                ));
-
-            hyperlink
-                .elements
-                .push(super::hyperlink::HyperlinkElement::Field(
-                    MutableField::separate(),
-                ));
-
-            let mut page_run = super::run::MutableRun::new();
-            page_run.set_text("1");
-            page_run.properties.no_proof = true;
-            page_run.properties.web_hidden = true;
-            hyperlink.add_run(page_run);
-
-            hyperlink
-                .elements
-                .push(super::hyperlink::HyperlinkElement::Field(
-                    MutableField::end(),
-                ));
-
-            toc_entry
-                .elements
-                .push(super::paragraph::ParagraphElement::Hyperlink(hyperlink));
+            content.push(HyperlinkElement::Run(text_run));
+
+            if toc.has_page_numbers() {
+                let mut tab_run = MutableRun::new();
+                tab_run.add_tab();
+                tab_run.properties.no_proof = true;
+                tab_run.properties.web_hidden = true;
+                content.push(HyperlinkElement::Run(tab_run));
+
+                content.push(HyperlinkElement::Field(MutableField::begin()));
+                content.push(HyperlinkElement::Field(MutableField::instruction_char(
+                    format!(" PAGEREF {} \\h ", bookmark_name),
+                )));
+                content.push(HyperlinkElement::Field(MutableField::separate()));
+
+                // Placeholder page number, replaced when fields are updated
+                let mut page_run = MutableRun::new();
+                page_run.set_text("1");
+                page_run.properties.no_proof = true;
+                page_run.properties.web_hidden = true;
+                content.push(HyperlinkElement::Run(page_run));
+
+                content.push(HyperlinkElement::Field(MutableField::end()));
+            }
+
+            if toc.has_hyperlinks() {
+                let mut hyperlink = MutableHyperlink::new_anchor(bookmark_name);
+                hyperlink.elements = content;
+                toc_entry
+                    .elements
+                    .push(ParagraphElement::Hyperlink(hyperlink));
+            } else {
+                toc_entry
+                    .elements
+                    .extend(content.into_iter().map(|element| match element {
+                        HyperlinkElement::Run(run) => ParagraphElement::Run(run),
+                        HyperlinkElement::Field(field) => ParagraphElement::Field(field),
+                    }));
+            }
+
             toc_paragraphs.push(toc_entry);
         }
 
-        // Add field end to the first TOC paragraph
-        if let Some(first_para) = toc_paragraphs.first_mut() {
-            first_para
-                .elements
-                .push(super::paragraph::ParagraphElement::Field(
-                    MutableField::end(),
-                ));
+        // The TOC field begins in the first entry; without headings it holds
+        // a placeholder until the field is updated
+        match toc_paragraphs.first_mut() {
+            Some(first) => {
+                first
+                    .elements
+                    .splice(0..0, field_start.into_iter().map(ParagraphElement::Field));
+            },
+            None => {
+                let mut placeholder = MutableParagraph::new();
+                placeholder
+                    .elements
+                    .extend(field_start.into_iter().map(ParagraphElement::Field));
+                placeholder.add_run_with_text("No table of contents entries found.");
+                toc_paragraphs.push(placeholder);
+            },
         }
 
+        // The field ends in its own paragraph after the entries
+        let mut field_end = MutableParagraph::new();
+        field_end
+            .elements
+            .push(ParagraphElement::Field(MutableField::end()));
+        toc_paragraphs.push(field_end);
+
         // Step 3: Insert TOC paragraphs at the recorded position
-        for (i, para) in toc_paragraphs.into_iter().enumerate() {
-            self.body
-                .elements
-                .insert(insertion_index + i, BodyElement::Paragraph(para));
-        }
+        self.body.elements.splice(
+            insertion_index..insertion_index,
+            toc_paragraphs.into_iter().map(BodyElement::Paragraph),
+        );
 
         Ok(())
     }
@@ -763,51 +1090,23 @@ impl MutableDocument {
     /// Generate header XML content.
     #[allow(dead_code)]
     pub(crate) fn generate_header_xml(&self) -> Result<Option<String>> {
-        if self.header.is_none() {
-            return Ok(None);
-        }
-
-        let mut xml = String::with_capacity(1024);
-        xml.push_str(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#);
-        xml.push_str(
-            r#"<w:hdr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
-        );
-        if let Some(ref paragraphs) = self.header {
-            if paragraphs.is_empty() {
-                xml.push_str(r#"<w:p><w:pPr><w:pStyle w:val="Header"/></w:pPr></w:p>"#);
-            } else {
-                for para in paragraphs {
-                    para.to_xml(&mut xml)?;
-                }
-            }
-        }
-        xml.push_str("</w:hdr>");
-        Ok(Some(xml))
+        self.generate_header_xml_of(WdHeaderFooter::Primary)
     }
 
     /// Generate footer XML content.
     #[allow(dead_code)]
     pub(crate) fn generate_footer_xml(&self) -> Result<Option<String>> {
-        if self.footer.is_none() {
-            return Ok(None);
-        }
+        self.generate_footer_xml_of(WdHeaderFooter::Primary)
+    }
 
-        let mut xml = String::with_capacity(1024);
-        xml.push_str(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#);
-        xml.push_str(
-            r#"<w:ftr xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#,
-        );
-        if let Some(ref paragraphs) = self.footer {
-            if paragraphs.is_empty() {
-                xml.push_str(r#"<w:p><w:pPr><w:pStyle w:val="Footer"/></w:pPr></w:p>"#);
-            } else {
-                for para in paragraphs {
-                    para.to_xml(&mut xml)?;
-                }
-            }
-        }
-        xml.push_str("</w:ftr>");
-        Ok(Some(xml))
+    /// Generate the XML of the header of the given kind.
+    pub(crate) fn generate_header_xml_of(&self, kind: WdHeaderFooter) -> Result<Option<String>> {
+        header_footer_xml(&self.headers, kind, "hdr", "Header")
+    }
+
+    /// Generate the XML of the footer of the given kind.
+    pub(crate) fn generate_footer_xml_of(&self, kind: WdHeaderFooter) -> Result<Option<String>> {
+        header_footer_xml(&self.footers, kind, "ftr", "Footer")
     }
 
     /// Generate footnotes XML content.
@@ -902,6 +1201,9 @@ impl MutableDocument {
         xml.push_str(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#);
         xml.push_str(r#"<w:settings xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">"#);
 
+        // Add default zoom (must precede the protection in the schema order)
+        xml.push_str(r#"<w:zoom w:percent="100"/>"#);
+
         // Add document protection if set
         if let Some(ref protection) = self.protection {
             xml.push_str(r#"<w:documentProtection w:edit=""#);
@@ -919,8 +1221,15 @@ impl MutableDocument {
             xml.push_str("/>");
         }
 
-        // Add default zoom
-        xml.push_str(r#"<w:zoom w:percent="100"/>"#);
+        // Different headers and footers on odd and even pages
+        if self.has_even_and_odd_headers() {
+            xml.push_str("<w:evenAndOddHeaders/>");
+        }
+
+        // Ask Word to refresh fields (e.g. TOC page numbers) on open
+        if self.update_fields {
+            xml.push_str(r#"<w:updateFields w:val="true"/>"#);
+        }
 
         xml.push_str("</w:settings>");
         Ok(xml)
@@ -936,6 +1245,96 @@ impl MutableDocument {
         self.body.table(index)
     }
 
+    /// Replace every occurrence of `old` with `new`, preserving formatting.
+    ///
+    /// Body paragraphs, table cells, the header and footer, footnotes and
+    /// endnotes are searched. A match may span several runs: the replacement
+    /// keeps the formatting of the first run, the other runs are shrunk to
+    /// their unmatched text or removed, and bookmarks inside the match are
+    /// widened to cover the replacement. Field instructions are left alone;
+    /// use [`replace_text_with`](Self::replace_text_with) to include them.
+    ///
+    /// Returns the number of replacements made.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::ooxml::docx::writer::MutableDocument;
+    ///
+    /// let mut doc = MutableDocument::new();
+    /// let para = doc.add_paragraph();
+    /// para.add_run_with_text("Version ").bold(true);
+    /// para.add_run_with_text("1.0 is out");
+    ///
+    /// // "Version 1.0" spans both runs; the result is a bold "Release 2.0".
+    /// assert_eq!(doc.replace_text("Version 1.0", "Release 2.0"), 1);
+    /// ```
+    pub fn replace_text(&mut self, old: &str, new: &str) -> usize {
+        self.replace_text_with(old, new, &ReplaceOptions::new())
+    }
+
+    /// Replace every occurrence of `old` with `new` using the given options.
+    pub fn replace_text_with(&mut self, old: &str, new: &str, options: &ReplaceOptions) -> usize {
+        if old.is_empty() {
+            return 0;
+        }
+        self.replace_all(&|text| replace::literal_edits(text, old, new), options)
+    }
+
+    /// Fill in `{{key}}` placeholders from a map in a single pass.
+    ///
+    /// Whitespace around the key is ignored (`{{ name }}` matches `name`) and
+    /// placeholders whose key is not in the map are left as they are.
+    /// Formatting is preserved as in [`replace_text`](Self::replace_text).
+    ///
+    /// Returns the number of placeholders replaced.
+    pub fn replace_placeholders<K, V, S>(&mut self, values: &HashMap<K, V, S>) -> usize
+    where
+        K: Borrow<str> + Eq + Hash,
+        V: AsRef<str>,
+        S: BuildHasher,
+    {
+        self.replace_all(
+            &|text| replace::placeholder_edits(text, |key| values.get(key).map(AsRef::as_ref)),
+            &ReplaceOptions::new(),
+        )
+    }
+
+    fn replace_all(&mut self, find: &replace::Finder<'_>, options: &ReplaceOptions) -> usize {
+        let mut count = 0;
+        for element in &mut self.body.elements {
+            match element {
+                BodyElement::Paragraph(para) => count += para.replace_with(find, options),
+                BodyElement::Table(table) => {
+                    for cell in table.rows.iter_mut().flat_map(|row| &mut row.cells) {
+                        for para in &mut cell.paragraphs {
+                            count += para.replace_with(find, options);
+                        }
+                    }
+                },
+                BodyElement::SectionBreak(_) => {},
+            }
+        }
+        let others = self
+            .headers
+            .iter_mut()
+            .chain(self.footers.iter_mut())
+            .flat_map(|(_, paragraphs)| paragraphs)
+            .chain(
+                self.footnotes
+                    .iter_mut()
+                    .chain(self.endnotes.iter_mut())
+                    .flat_map(|note| &mut note.paragraphs),
+            );
+        for para in others {
+            count += para.replace_with(find, options);
+        }
+        if count > 0 {
+            self.modified = true;
+        }
+        count
+    }
+
     /// Serialize the document to XML.
     pub fn to_xml(&self) -> Result<String> {
         let mut xml = String::with_capacity(4096);
@@ -950,99 +1349,193 @@ impl MutableDocument {
     ///
     /// This is the correct method to use when saving documents, as it includes
     /// proper relationship IDs and section properties with header/footer references.
-    pub(crate) fn to_xml_with_rels(
-        &self,
-        rel_mapper: &super::relmap::RelationshipMapper,
-    ) -> Result<String> {
+    pub(crate) fn to_xml_with_rels(&self, rel_mapper: &RelationshipMapper) -> Result<String> {
         let mut xml = String::with_capacity(4096);
         xml.push_str(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#);
         xml.push_str(r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">"#);
 
         // Generate body with relationship IDs
-        self.body.to_xml_with_rels(&mut xml, rel_mapper)?;
+        let title_page = self.has_title_page();
+        self.body
+            .to_xml_with_rels(&mut xml, rel_mapper, title_page)?;
 
         // Add section properties at the end of the body (before </w:body>)
         // The sectPr must be the last element in the body
-        self.generate_section_properties(&mut xml, rel_mapper)?;
+        write_section_properties(&mut xml, &self.section, rel_mapper, title_page)?;
 
         xml.push_str("</w:body>");
         xml.push_str("</w:document>");
         Ok(xml)
     }
+}
 
-    /// Generate section properties XML including header/footer/footnote/endnote references.
-    fn generate_section_properties(
-        &self,
-        xml: &mut String,
-        rel_mapper: &super::relmap::RelationshipMapper,
-    ) -> Result<()> {
-        xml.push_str("<w:sectPr>");
-
-        // IMPORTANT: Element order MUST follow OOXML spec (ISO/IEC 29500)
-        // Microsoft Word strictly enforces this ordering!
-
-        // 1. Add header reference if present (must come before footnotePr)
-        if let Some(header_id) = rel_mapper.get_header_id() {
-            write!(
-                xml,
-                r#"<w:headerReference w:type="default" r:id="{}"/>"#,
-                header_id
-            )
-            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-        }
+/// Write the `<w:sectPr>` of a section, including header/footer/footnote/endnote references.
+fn write_section_properties(
+    xml: &mut String,
+    section: &SectionProperties,
+    rel_mapper: &RelationshipMapper,
+    title_page: bool,
+) -> Result<()> {
+    xml.push_str("<w:sectPr>");
 
-        // 2. Add footer reference if present (must come before footnotePr)
-        if let Some(footer_id) = rel_mapper.get_footer_id() {
-            write!(
-                xml,
-                r#"<w:footerReference w:type="default" r:id="{}"/>"#,
-                footer_id
-            )
-            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-        }
+    // IMPORTANT: Element order MUST follow OOXML spec (ISO/IEC 29500)
+    // Microsoft Word strictly enforces this ordering!
 
-        // 3. Add footnote properties if present
-        if rel_mapper.get_footnotes_id().is_some() {
-            write!(
-                xml,
-                r#"<w:footnotePr><w:numFmt w:val="decimal"/></w:footnotePr>"#
-            )
-            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-        }
+    // 1. Add header references if present (must come before footnotePr)
+    for (kind, header_id) in rel_mapper.header_references() {
+        write!(
+            xml,
+            r#"<w:headerReference w:type="{}" r:id="{}"/>"#,
+            kind.to_xml(),
+            header_id
+        )
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+    }
 
-        // 4. Add endnote properties if present
-        if rel_mapper.get_endnotes_id().is_some() {
-            write!(
-                xml,
-                r#"<w:endnotePr><w:numFmt w:val="decimal"/></w:endnotePr>"#
-            )
-            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-        }
+    // 2. Add footer references if present (must come before footnotePr)
+    for (kind, footer_id) in rel_mapper.footer_references() {
+        write!(
+            xml,
+            r#"<w:footerReference w:type="{}" r:id="{}"/>"#,
+            kind.to_xml(),
+            footer_id
+        )
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+    }
 
-        // Add page size and margins
+    // 3. Add footnote properties if present
+    if rel_mapper.get_footnotes_id().is_some() {
         write!(
             xml,
-            r#"<w:pgSz w:w="{}" w:h="{}" w:orient="{}"/>"#,
-            self.section.page_width,
-            self.section.page_height,
-            self.section.orientation.as_str()
+            r#"<w:footnotePr><w:numFmt w:val="decimal"/></w:footnotePr>"#
         )
         .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+    }
 
+    // 4. Add endnote properties if present
+    if rel_mapper.get_endnotes_id().is_some() {
         write!(
             xml,
-            r#"<w:pgMar w:top="{}" w:right="{}" w:bottom="{}" w:left="{}" w:header="{}" w:footer="{}"/>"#,
-            self.section.margin_top,
-            self.section.margin_right,
-            self.section.margin_bottom,
-            self.section.margin_left,
-            self.section.header_distance,
-            self.section.footer_distance
-        ).map_err(|e| OoxmlError::Xml(e.to_string()))?;
+            r#"<w:endnotePr><w:numFmt w:val="decimal"/></w:endnotePr>"#
+        )
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+    }
 
-        xml.push_str("</w:sectPr>");
-        Ok(())
+    // Add page size and margins
+    write!(
+        xml,
+        r#"<w:pgSz w:w="{}" w:h="{}" w:orient="{}"/>"#,
+        section.page_width,
+        section.page_height,
+        section.orientation.as_str()
+    )
+    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+
+    write!(
+        xml,
+        r#"<w:pgMar w:top="{}" w:right="{}" w:bottom="{}" w:left="{}" w:header="{}" w:footer="{}"/>"#,
+        section.margin_top,
+        section.margin_right,
+        section.margin_bottom,
+        section.margin_left,
+        section.header_distance,
+        section.footer_distance
+    ).map_err(|e| OoxmlError::Xml(e.to_string()))?;
+
+    // Page numbering
+    if section.page_number_start.is_some() || section.page_number_format.is_some() {
+        xml.push_str("<w:pgNumType");
+        if let Some(format) = section.page_number_format {
+            write!(xml, r#" w:fmt="{}""#, format.as_str())
+                .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        }
+        if let Some(start) = section.page_number_start {
+            write!(xml, r#" w:start="{}""#, start).map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        }
+        xml.push_str("/>");
+    }
+
+    // Text columns
+    if section.columns > 1 {
+        write!(
+            xml,
+            r#"<w:cols w:num="{}" w:space="{}"/>"#,
+            section.columns, section.column_spacing
+        )
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+    }
+
+    // Different first page header and footer
+    if title_page {
+        xml.push_str("<w:titlePg/>");
+    }
+
+    xml.push_str("</w:sectPr>");
+    Ok(())
+}
+
+/// Write a section break: an empty paragraph carrying the `<w:sectPr>` of
+/// the section it ends.
+fn section_break_xml(
+    xml: &mut String,
+    section: &SectionProperties,
+    rel_mapper: &RelationshipMapper,
+    title_page: bool,
+) -> Result<()> {
+    xml.push_str("<w:p><w:pPr>");
+    write_section_properties(xml, section, rel_mapper, title_page)?;
+    xml.push_str("</w:pPr></w:p>");
+    Ok(())
+}
+
+/// Get or create the header or footer content of the given kind.
+fn header_footer_slot(
+    slots: &mut Vec<(WdHeaderFooter, Vec<MutableParagraph>)>,
+    kind: WdHeaderFooter,
+) -> &mut Vec<MutableParagraph> {
+    let index = match slots.iter().position(|(k, _)| *k == kind) {
+        Some(index) => index,
+        None => {
+            slots.push((kind, Vec::new()));
+            slots.len() - 1
+        },
+    };
+    &mut slots[index].1
+}
+
+/// Generate the XML of a header (`hdr`) or footer (`ftr`) part.
+fn header_footer_xml(
+    slots: &[(WdHeaderFooter, Vec<MutableParagraph>)],
+    kind: WdHeaderFooter,
+    root: &str,
+    style: &str,
+) -> Result<Option<String>> {
+    let Some((_, paragraphs)) = slots.iter().find(|(k, _)| *k == kind) else {
+        return Ok(None);
+    };
+
+    let mut xml = String::with_capacity(1024);
+    xml.push_str(r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#);
+    write!(
+        xml,
+        r#"<w:{} xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">"#,
+        root
+    )
+    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+    if paragraphs.is_empty() {
+        write!(
+            xml,
+            r#"<w:p><w:pPr><w:pStyle w:val="{}"/></w:pPr></w:p>"#,
+            style
+        )
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+    } else {
+        for para in paragraphs {
+            para.to_xml(&mut xml)?;
+        }
     }
+    write!(xml, "</w:{}>", root).map_err(|e| OoxmlError::Xml(e.to_string()))?;
+    Ok(Some(xml))
 }
 
 impl Default for MutableDocument {
@@ -1269,6 +1762,9 @@ impl DocumentBody {
             match element {
                 BodyElement::Paragraph(p) => p.to_xml(xml)?,
                 BodyElement::Table(t) => t.to_xml(xml)?,
+                BodyElement::SectionBreak(section) => {
+                    section_break_xml(xml, section, &RelationshipMapper::new(), false)?
+                },
             }
         }
 
@@ -1289,7 +1785,8 @@ impl DocumentBody {
     fn to_xml_with_rels(
         &self,
         xml: &mut String,
-        rel_mapper: &crate::ooxml::docx::writer::relmap::RelationshipMapper,
+        rel_mapper: &RelationshipMapper,
+        title_page: bool,
     ) -> Result<()> {
         xml.push_str("<w:body>");
 
@@ -1308,6 +1805,9 @@ impl DocumentBody {
                     )?;
                 },
                 BodyElement::Table(t) => t.to_xml(xml)?, // Tables don't need rel mapping for now
+                BodyElement::SectionBreak(section) => {
+                    section_break_xml(xml, section, rel_mapper, title_page)?
+                },
             }
         }
 
@@ -1316,11 +1816,21 @@ impl DocumentBody {
     }
 }
 
-/// A body element (paragraph or table).
+/// A body element (paragraph, table or section break).
 #[derive(Debug)]
 pub(crate) enum BodyElement {
     Paragraph(MutableParagraph),
     Table(MutableTable),
+    /// End of a section, holding the properties of the section it ends
+    SectionBreak(SectionProperties),
+}
+
+/// Get the level of a heading paragraph style.
+///
+/// Accepts both the style ID (`Heading1`) and the style name (`Heading 1`).
+fn heading_level(style: &str) -> Option<u8> {
+    let level = style.strip_prefix("Heading")?.trim_start().parse().ok()?;
+    (1..=9).contains(&level).then_some(level)
 }
 
 #[cfg(test)]
@@ -1373,4 +1883,44 @@ mod tests {
         assert!(xml.contains("<w:b/>"));
         assert!(xml.contains("<w:i/>"));
     }
+
+    #[test]
+    fn test_table_of_contents() {
+        let mut doc = MutableDocument::new();
+        doc.add_table_of_contents(TableOfContents::new().title("Contents"))
+            .unwrap();
+        doc.add_heading("Introduction", 1).unwrap();
+        doc.add_paragraph_with_text("Body text");
+        doc.add_heading("Details", 2).unwrap();
+        doc.add_heading("Too deep", 4).unwrap();
+        doc.generate_toc_if_needed().unwrap();
+
+        let xml = doc.to_xml().unwrap();
+        let toc_start = xml.find(r#"<w:fldChar w:fldCharType="begin"/>"#).unwrap();
+        let first_entry = xml.find(r#"<w:pStyle w:val="TOC1"/>"#).unwrap();
+        let toc_end = xml.rfind(r#"<w:fldChar w:fldCharType="end"/>"#).unwrap();
+        let first_heading = xml.find(r#"<w:pStyle w:val="Heading1"/>"#).unwrap();
+
+        // The entries lie inside the TOC field, which ends before the headings
+        assert!(xml.contains(r#"TOC \o &quot;1-3&quot; \h \z \u"#));
+        assert!(first_entry < toc_start && toc_start < toc_end && toc_end < first_heading);
+        assert!(xml.contains(r#"<w:pStyle w:val="TOC2"/>"#));
+
+        // Headings are bookmarked and linked from their entries
+        assert!(xml.contains(r#"<w:hyperlink w:anchor="_Toc213359267">"#));
+        assert!(xml.contains(r#"<w:bookmarkStart w:id="0" w:name="_Toc213359267"/>"#));
+        assert!(xml.contains("PAGEREF _Toc213359268 \\h"));
+        assert!(!xml.contains("Toc213359269"));
+
+        let settings = doc.generate_settings_xml().unwrap();
+        assert!(settings.contains(r#"<w:updateFields w:val="true"/>"#));
+    }
+
+    #[test]
+    fn test_heading_level() {
+        assert_eq!(heading_level("Heading1"), Some(1));
+        assert_eq!(heading_level("Heading 9"), Some(9));
+        assert_eq!(heading_level("Heading10"), None);
+        assert_eq!(heading_level("Title"), None);
+    }
 }
@@ -0,0 +1,660 @@
+//! Form filling for the content controls of an existing document.
+//!
+//! Values are written straight into the document XML: only the properties and
+//! content of the filled controls change, everything else is kept byte for
+//! byte. Filling a control:
+//!
+//! - replaces its content with a single run showing the value, formatted like
+//!   the first run it held (or like the control's own run properties when it
+//!   was showing its placeholder text);
+//! - clears the placeholder flag;
+//! - updates the `w14:checked` state of checkboxes, the `w:fullDate` of date
+//!   pickers and the `w:lastValue` of drop-down lists and combo boxes;
+//! - reports the new value of data-bound controls so the bound node of the
+//!   custom XML part can be updated as well (see [`update_bound_node`]).
+//!
+//! When both a control and a control nested in it are filled, the outer value
+//! wins.
+
+use super::content_control::ContentControlValue;
+use super::merge::format_date_time;
+use crate::common::xml::escape_xml;
+use crate::ooxml::docx::content_control::{
+    ContentControl, DataBinding, SdtLayout, scan_content_controls,
+};
+use crate::ooxml::error::{OoxmlError, Result};
+use chrono::{NaiveDate, NaiveTime};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::{NsReader, Reader};
+use std::fmt::Write as FmtWrite;
+use std::ops::Range;
+
+/// Glyphs Word uses for checkboxes without explicit states.
+const CHECKED_GLYPH: char = '\u{2612}';
+const UNCHECKED_GLYPH: char = '\u{2610}';
+
+/// Date format of date pickers without an explicit one.
+const DEFAULT_DATE_FORMAT: &str = "M/d/yyyy";
+
+/// What filling a control with a value amounts to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Fill {
+    /// Text shown in the control
+    text: String,
+    /// Value stored in the bound custom XML node
+    bound: String,
+    checked: Option<bool>,
+    date: Option<NaiveDate>,
+    /// Selected list entry
+    last_value: Option<String>,
+}
+
+/// A bound custom XML node to update after filling.
+#[derive(Debug, Clone)]
+pub(crate) struct BindingUpdate {
+    pub(crate) binding: DataBinding,
+    pub(crate) value: String,
+}
+
+/// Check that `value` suits `control` and work out what it fills in.
+pub(crate) fn resolve(control: &ContentControl, value: &ContentControlValue) -> Result<Fill> {
+    let name = control.tag().or(control.title()).unwrap_or_default();
+    let mismatch = |expected: &str| {
+        Err(OoxmlError::InvalidFormat(format!(
+            "content control '{}' expects {}, got {:?}",
+            name, expected, value
+        )))
+    };
+    let text = |text: &str| Fill {
+        text: text.to_string(),
+        bound: text.to_string(),
+        checked: None,
+        date: None,
+        last_value: None,
+    };
+
+    match (control.control_type(), value) {
+        (Some("checkbox"), ContentControlValue::Checked(checked)) => Ok(Fill {
+            checked: Some(*checked),
+            ..text(if *checked { "true" } else { "false" })
+        }),
+        (Some("checkbox"), _) => mismatch("a checked state"),
+        (_, ContentControlValue::Checked(_)) => mismatch("text"),
+        (Some("picture"), _) => Err(OoxmlError::InvalidFormat(format!(
+            "picture content control '{}' cannot be filled with a value",
+            name
+        ))),
+        (Some("date"), ContentControlValue::Date(date)) => Ok(Fill {
+            text: format_date(*date, control.date_format().unwrap_or(DEFAULT_DATE_FORMAT)),
+            bound: date.format("%Y-%m-%dT00:00:00").to_string(),
+            checked: None,
+            date: Some(*date),
+            last_value: None,
+        }),
+        (_, ContentControlValue::Date(_)) => mismatch("text"),
+        (Some(kind @ ("dropDownList" | "comboBox")), ContentControlValue::Text(entry))
+        | (Some(kind @ ("dropDownList" | "comboBox")), ContentControlValue::Item(entry)) => {
+            let item = control
+                .list_items()
+                .iter()
+                .find(|(_, value)| value == entry)
+                .or_else(|| {
+                    control
+                        .list_items()
+                        .iter()
+                        .find(|(display, _)| display == entry)
+                });
+            match item {
+                Some((display, value)) => Ok(Fill {
+                    last_value: Some(value.clone()),
+                    bound: value.clone(),
+                    ..text(display)
+                }),
+                // Combo boxes take any text as well
+                None if kind == "comboBox" && matches!(value, ContentControlValue::Text(_)) => {
+                    Ok(Fill {
+                        last_value: Some(entry.clone()),
+                        ..text(entry)
+                    })
+                },
+                None => Err(OoxmlError::InvalidFormat(format!(
+                    "'{}' is not an entry of content control '{}'",
+                    entry, name
+                ))),
+            }
+        },
+        (_, ContentControlValue::Item(_)) => mismatch("text"),
+        (_, ContentControlValue::Text(value)) => Ok(text(value)),
+    }
+}
+
+/// Fill the content controls of `xml` whose tag has a value in `values`.
+///
+/// Later values for a tag win over earlier ones. Returns the new XML and
+/// the custom XML nodes to update.
+pub(crate) fn fill_content_controls(
+    xml: &[u8],
+    values: &[(String, ContentControlValue)],
+) -> Result<(Vec<u8>, Vec<BindingUpdate>)> {
+    let mut edits: Vec<(Range<usize>, Vec<u8>)> = Vec::new();
+    let mut updates = Vec::new();
+
+    for (control, layout) in scan_content_controls(xml)? {
+        let Some(tag) = control.tag() else { continue };
+        let Some((_, value)) = values.iter().rev().find(|(t, _)| t == tag) else {
+            continue;
+        };
+        let fill = resolve(&control, value)?;
+        fill_control(xml, &control, &layout, &fill, &mut edits)?;
+        if let Some(binding) = control.data_binding() {
+            updates.push(BindingUpdate {
+                binding: binding.clone(),
+                value: fill.bound,
+            });
+        }
+    }
+
+    edits.sort_by_key(|(range, _)| range.start);
+    let mut out = Vec::with_capacity(xml.len());
+    let mut pos = 0;
+    for (range, replacement) in edits {
+        // Inside content that has been replaced already
+        if range.start < pos {
+            continue;
+        }
+        out.extend_from_slice(&xml[pos..range.start]);
+        out.extend_from_slice(&replacement);
+        pos = range.end;
+    }
+    out.extend_from_slice(&xml[pos..]);
+
+    Ok((out, updates))
+}
+
+/// Queue the edits that fill one control.
+fn fill_control(
+    xml: &[u8],
+    control: &ContentControl,
+    layout: &SdtLayout,
+    fill: &Fill,
+    edits: &mut Vec<(Range<usize>, Vec<u8>)>,
+) -> Result<()> {
+    let name = control.tag().unwrap_or_default();
+    let Some(content) = layout.content.clone() else {
+        return Err(OoxmlError::InvalidFormat(format!(
+            "content control '{}' has no content to fill",
+            name
+        )));
+    };
+    if layout.has_cells {
+        return Err(OoxmlError::InvalidFormat(format!(
+            "content control '{}' wraps table rows or cells and cannot be filled",
+            name
+        )));
+    }
+
+    if let Some(range) = layout.placeholder_flag.clone() {
+        edits.push((range, Vec::new()));
+    }
+    if let (Some(checked), Some(range)) = (fill.checked, layout.checked.clone()) {
+        let tag = set_attribute(
+            &xml[range.clone()],
+            "val",
+            Some(if checked { "1" } else { "0" }),
+        )?;
+        edits.push((range, tag));
+    }
+    if let Some(range) = layout.date.clone() {
+        let date = fill
+            .date
+            .map(|date| date.format("%Y-%m-%dT00:00:00Z").to_string());
+        edits.push((
+            range.clone(),
+            set_attribute(&xml[range], "fullDate", date.as_deref())?,
+        ));
+    }
+    if let (Some(value), Some(range)) = (&fill.last_value, layout.list.clone()) {
+        edits.push((
+            range.clone(),
+            set_attribute(&xml[range], "lastValue", Some(value))?,
+        ));
+    }
+
+    let p = layout.prefix.as_str();
+    let mut run_properties = if control.is_showing_placeholder() {
+        layout.run_properties.clone()
+    } else {
+        layout.first_run_properties.clone()
+    }
+    .map(|range| String::from_utf8_lossy(&xml[range]).into_owned());
+
+    let text = match fill.checked {
+        Some(checked) => {
+            let state = if checked {
+                &layout.checked_state
+            } else {
+                &layout.unchecked_state
+            };
+            let default = if checked {
+                CHECKED_GLYPH
+            } else {
+                UNCHECKED_GLYPH
+            };
+            let (glyph, font) = state.clone().unwrap_or((default, None));
+            if run_properties.is_none()
+                && let Some(font) = font
+            {
+                let font = escape_xml(&font);
+                run_properties = Some(format!(
+                    r#"<{p}rPr><{p}rFonts {p}ascii="{font}" {p}eastAsia="{font}" {p}hAnsi="{font}" {p}hint="eastAsia"/></{p}rPr>"#
+                ));
+            }
+            glyph.to_string()
+        },
+        None => fill.text.clone(),
+    };
+
+    let run = text_run(p, run_properties.as_deref(), &text);
+
+    let mut body = String::new();
+    write!(body, "<{p}sdtContent>").ok();
+    if layout.block {
+        let start = layout
+            .first_paragraph
+            .clone()
+            .map(|range| String::from_utf8_lossy(&xml[range]).into_owned())
+            .unwrap_or_else(|| format!("<{p}p>"));
+        match start.strip_suffix("/>") {
+            Some(open) => {
+                body.push_str(open.trim_end());
+                body.push('>');
+            },
+            None => body.push_str(&start),
+        }
+        if let Some(range) = &layout.first_paragraph_properties {
+            body.push_str(&String::from_utf8_lossy(&xml[range.clone()]));
+        }
+        write!(body, "{run}</{p}p>").ok();
+    } else {
+        body.push_str(&run);
+    }
+    write!(body, "</{p}sdtContent>").ok();
+    edits.push((content, body.into_bytes()));
+
+    Ok(())
+}
+
+/// Rewrite a start tag with the attribute of local name `name` set to
+/// `value`, or removed when `value` is `None`.
+///
+/// A missing attribute is added with the prefix of the element.
+fn set_attribute(tag: &[u8], name: &str, value: Option<&str>) -> Result<Vec<u8>> {
+    let mut reader = Reader::from_reader(tag);
+    let (start, empty) = match reader
+        .read_event()
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?
+    {
+        Event::Start(e) => (e, false),
+        Event::Empty(e) => (e, true),
+        _ => return Err(OoxmlError::Xml("expected a start tag".to_string())),
+    };
+
+    let element = String::from_utf8_lossy(start.name().as_ref()).into_owned();
+    let mut key = match start.name().prefix() {
+        Some(prefix) => format!("{}:{}", String::from_utf8_lossy(prefix.as_ref()), name),
+        None => name.to_string(),
+    };
+    let mut out = BytesStart::new(element);
+    for attr in start.attributes() {
+        let attr = attr.map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        if attr.key.local_name().as_ref() == name.as_bytes() {
+            key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+        } else {
+            out.push_attribute(attr);
+        }
+    }
+    if let Some(value) = value {
+        out.push_attribute((key.as_str(), value));
+    }
+
+    let mut bytes = Vec::with_capacity(tag.len() + 16);
+    bytes.push(b'<');
+    bytes.extend_from_slice(&out);
+    bytes.extend_from_slice(if empty { b"/>" } else { b">" });
+    Ok(bytes)
+}
+
+/// Format `date` with a Word date picker format such as `dddd, MMMM d, yyyy`.
+pub(crate) fn format_date(date: NaiveDate, format: &str) -> String {
+    format_date_time(date.and_time(NaiveTime::MIN), format)
+}
+
+/// Build a run showing `text`, with `\n` as line breaks and `\t` as tabs.
+pub(crate) fn text_run(prefix: &str, properties: Option<&str>, text: &str) -> String {
+    let p = prefix;
+    let mut run = format!("<{p}r>{}", properties.unwrap_or_default());
+    for (i, line) in text.split('\n').enumerate() {
+        if i > 0 {
+            write!(run, "<{p}br/>").ok();
+        }
+        for (j, segment) in line.split('\t').enumerate() {
+            if j > 0 {
+                write!(run, "<{p}tab/>").ok();
+            }
+            if !segment.is_empty() {
+                write!(
+                    run,
+                    r#"<{p}t xml:space="preserve">{}</{p}t>"#,
+                    escape_xml(segment)
+                )
+                .ok();
+            }
+        }
+    }
+    write!(run, "</{p}r>").ok();
+    run
+}
+
+/// Get the `ds:itemID` of a custom XML properties part.
+pub(crate) fn store_item_id(props_xml: &[u8]) -> Option<String> {
+    let mut reader = Reader::from_reader(props_xml);
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(e) | Event::Empty(e) => {
+                return e
+                    .attributes()
+                    .flatten()
+                    .find(|attr| attr.key.local_name().as_ref() == b"itemID")
+                    .and_then(|attr| attr.unescape_value().ok())
+                    .map(|value| value.into_owned());
+            },
+            Event::Eof => return None,
+            _ => {},
+        }
+    }
+}
+
+/// One step of a bound XPath such as `ns0:name[1]`.
+struct Step {
+    namespace: Option<String>,
+    local: String,
+    position: usize,
+}
+
+/// Parse the element steps of a bound XPath.
+///
+/// Word writes absolute paths of positional element steps only; anything else
+/// (attributes, predicates, wildcards) gives `None`.
+fn parse_xpath(xpath: &str, prefix_mappings: &str) -> Option<Vec<Step>> {
+    let namespaces: Vec<(&str, &str)> = prefix_mappings
+        .split_whitespace()
+        .filter_map(|decl| {
+            let (prefix, uri) = decl.strip_prefix("xmlns:")?.split_once('=')?;
+            Some((prefix, uri.trim_matches(['\'', '"'])))
+        })
+        .collect();
+
+    xpath
+        .strip_prefix('/')?
+        .split('/')
+        .map(|step| {
+            let (name, position) = match step.split_once('[') {
+                Some((name, predicate)) => (name, predicate.strip_suffix(']')?.parse().ok()?),
+                None => (step, 1),
+            };
+            let (namespace, local) = match name.split_once(':') {
+                Some((prefix, local)) => {
+                    let uri = namespaces.iter().find(|(p, _)| *p == prefix)?.1;
+                    (Some(uri.to_string()), local)
+                },
+                None => (None, name),
+            };
+            let valid = |s: &str| {
+                !s.is_empty()
+                    && s.chars()
+                        .all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+            };
+            valid(local).then(|| Step {
+                namespace,
+                local: local.to_string(),
+                position,
+            })
+        })
+        .collect()
+}
+
+/// Set the text of the node `binding` points at in the custom XML `xml`.
+///
+/// Returns `None` when the path cannot be followed or selects nothing.
+pub(crate) fn update_bound_node(
+    xml: &[u8],
+    binding: &DataBinding,
+    value: &str,
+) -> Result<Option<Vec<u8>>> {
+    let Some(steps) = parse_xpath(binding.xpath(), binding.prefix_mappings()) else {
+        return Ok(None);
+    };
+    let matches = |step: &Step, ns: &Option<Vec<u8>>, local: &[u8]| {
+        step.local.as_bytes() == local
+            && step.namespace.as_deref().map(str::as_bytes) == ns.as_deref()
+    };
+
+    let mut reader = NsReader::from_reader(xml);
+    // Open elements, steps matched along them, and matching siblings seen
+    // among the children of each open element
+    let mut depth = 0;
+    let mut matched = 0;
+    let mut counts = vec![0];
+    let mut content_start = None;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let (ns, event) = reader
+            .read_resolved_event()
+            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        let ns = match ns {
+            ResolveResult::Bound(Namespace(uri)) => Some(uri.to_vec()),
+            _ => None,
+        };
+        let end = reader.buffer_position() as usize;
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) if content_start.is_none() => {
+                let empty = matches!(event, Event::Empty(_));
+                let hit = matched == depth
+                    && depth < steps.len()
+                    && matches(&steps[depth], &ns, e.local_name().as_ref());
+                if hit {
+                    counts[depth] += 1;
+                }
+                let selected = hit && counts[depth] == steps[depth].position;
+
+                if selected && depth + 1 == steps.len() {
+                    let text = escape_xml(value);
+                    if !empty {
+                        content_start = Some(end);
+                    } else {
+                        let tag = String::from_utf8_lossy(&xml[start..end]);
+                        let open = tag.trim_end_matches("/>").trim_end();
+                        let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+                        let mut out = xml[..start].to_vec();
+                        out.extend_from_slice(format!("{open}>{text}</{name}>").as_bytes());
+                        out.extend_from_slice(&xml[end..]);
+                        return Ok(Some(out));
+                    }
+                }
+                if !empty {
+                    depth += 1;
+                    if selected {
+                        matched = depth;
+                    }
+                    counts.truncate(depth);
+                    counts.push(0);
+                }
+            },
+            Event::Start(_) => depth += 1,
+            Event::End(_) => {
+                depth -= 1;
+                if let Some(content_start) = content_start
+                    && depth + 1 == steps.len()
+                {
+                    let mut out = xml[..content_start].to_vec();
+                    out.extend_from_slice(escape_xml(value).as_bytes());
+                    out.extend_from_slice(&xml[start..]);
+                    return Ok(Some(out));
+                }
+                matched = matched.min(depth);
+            },
+            Event::Eof => return Ok(None),
+            _ => {},
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORM: &str = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main" xmlns:w14="http://schemas.microsoft.com/office/word/2010/wordml"><w:body>
+<w:sdt><w:sdtPr><w:rPr><w:b/></w:rPr><w:alias w:val="Name"/><w:tag w:val="name"/><w:id w:val="-1203946"/><w:showingPlcHdr/><w:text/></w:sdtPr><w:sdtContent><w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:rPr><w:rStyle w:val="PlaceholderText"/></w:rPr><w:t>Click here</w:t></w:r></w:p></w:sdtContent></w:sdt>
+<w:p><w:r><w:t>Agree: </w:t></w:r><w:sdt><w:sdtPr><w:tag w:val="agree"/><w:id w:val="7"/><w14:checkbox><w14:checked w14:val="0"/><w14:checkedState w14:val="2612" w14:font="MS Gothic"/><w14:uncheckedState w14:val="2610" w14:font="MS Gothic"/></w14:checkbox></w:sdtPr><w:sdtContent><w:r><w:rPr><w:rFonts w:ascii="MS Gothic"/></w:rPr><w:t>☐</w:t></w:r></w:sdtContent></w:sdt></w:p>
+<w:p><w:sdt><w:sdtPr><w:tag w:val="color"/><w:id w:val="8"/><w:dropDownList><w:listItem w:displayText="Red" w:value="R"/><w:listItem w:displayText="Blue" w:value="B"/></w:dropDownList></w:sdtPr><w:sdtContent><w:r><w:t>Red</w:t></w:r></w:sdtContent></w:sdt></w:p>
+<w:p><w:sdt><w:sdtPr><w:tag w:val="due"/><w:id w:val="9"/><w:date><w:dateFormat w:val="dddd, MMMM d, yyyy"/></w:date></w:sdtPr><w:sdtContent><w:r><w:t>Pick a date</w:t></w:r></w:sdtContent></w:sdt></w:p>
+</w:body></w:document>"#;
+
+    fn values(pairs: &[(&str, ContentControlValue)]) -> Vec<(String, ContentControlValue)> {
+        pairs
+            .iter()
+            .map(|(tag, value)| (tag.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_content_controls() {
+        let controls = ContentControl::extract_from_document(FORM.as_bytes()).unwrap();
+        assert_eq!(controls.len(), 4);
+
+        assert_eq!(controls[0].title(), Some("Name"));
+        assert_eq!(controls[0].id(), -1203946i32 as u32);
+        assert!(controls[0].is_showing_placeholder());
+        assert_eq!(controls[0].value(), "Click here");
+
+        assert_eq!(controls[1].control_type(), Some("checkbox"));
+        assert_eq!(controls[1].is_checked(), Some(false));
+
+        assert_eq!(controls[2].control_type(), Some("dropDownList"));
+        assert_eq!(
+            controls[2].list_items(),
+            [
+                ("Red".to_string(), "R".to_string()),
+                ("Blue".to_string(), "B".to_string())
+            ]
+        );
+
+        assert_eq!(controls[3].date_format(), Some("dddd, MMMM d, yyyy"));
+        assert_eq!(controls[3].date(), None);
+    }
+
+    #[test]
+    fn test_fill_content_controls() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        let (xml, updates) = fill_content_controls(
+            FORM.as_bytes(),
+            &values(&[
+                ("name", "Ada\nLovelace".into()),
+                ("agree", true.into()),
+                ("color", ContentControlValue::Item("Blue".to_string())),
+                ("due", date.into()),
+            ]),
+        )
+        .unwrap();
+        assert!(updates.is_empty());
+
+        let text = String::from_utf8(xml.clone()).unwrap();
+        // Block control keeps its paragraph properties and takes the
+        // control's run properties instead of the placeholder style
+        assert!(text.contains(r#"<w:sdtContent><w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:rPr><w:b/></w:rPr><w:t xml:space="preserve">Ada</w:t><w:br/>"#));
+        assert!(!text.contains("showingPlcHdr"));
+        assert!(text.contains(r#"<w14:checked w14:val="1"/>"#));
+        assert!(text.contains(r#"<w:dropDownList w:lastValue="B">"#));
+        assert!(text.contains(r#"<w:date w:fullDate="2024-03-05T00:00:00Z">"#));
+
+        let controls = ContentControl::extract_from_document(&xml).unwrap();
+        assert_eq!(controls[0].value(), "Ada\nLovelace");
+        assert!(!controls[0].is_showing_placeholder());
+        assert_eq!(controls[1].is_checked(), Some(true));
+        assert_eq!(controls[1].value(), "☒");
+        assert_eq!(controls[2].value(), "Blue");
+        assert_eq!(controls[3].value(), "Tuesday, March 5, 2024");
+        assert_eq!(controls[3].date(), Some(date));
+
+        // Filling again gives the same document
+        let (again, _) = fill_content_controls(
+            &xml,
+            &values(&[("name", "Ada\nLovelace".into()), ("agree", true.into())]),
+        )
+        .unwrap();
+        assert_eq!(again, xml);
+    }
+
+    #[test]
+    fn test_resolve_rejects_mismatched_values() {
+        let controls = ContentControl::extract_from_document(FORM.as_bytes()).unwrap();
+        assert!(resolve(&controls[0], &true.into()).is_err());
+        assert!(resolve(&controls[1], &"yes".into()).is_err());
+        assert!(resolve(&controls[2], &"Green".into()).is_err());
+        assert_eq!(
+            resolve(&controls[2], &"R".into()).unwrap().text,
+            "Red".to_string()
+        );
+    }
+
+    #[test]
+    fn test_format_date() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+        assert_eq!(format_date(date, DEFAULT_DATE_FORMAT), "3/5/2024");
+        assert_eq!(format_date(date, "dd.MM.yy"), "05.03.24");
+        assert_eq!(format_date(date, "d MMM yyyy"), "5 Mar 2024");
+        assert_eq!(format_date(date, "'Week of' d"), "Week of 5");
+    }
+
+    #[test]
+    fn test_update_bound_node() {
+        let xml = br#"<?xml version="1.0"?><ns0:form xmlns:ns0="urn:form"><ns0:name>Old</ns0:name><ns0:name/><other/></ns0:form>"#;
+        let binding = |xpath: &str| DataBinding {
+            xpath: xpath.to_string(),
+            store_item_id: None,
+            prefix_mappings: "xmlns:ns0='urn:form'".to_string(),
+        };
+
+        let updated = update_bound_node(xml, &binding("/ns0:form[1]/ns0:name[1]"), "A & B")
+            .unwrap()
+            .unwrap();
+        assert!(
+            String::from_utf8(updated)
+                .unwrap()
+                .contains("<ns0:name>A &amp; B</ns0:name><ns0:name/>")
+        );
+
+        let updated = update_bound_node(xml, &binding("/ns0:form[1]/ns0:name[2]"), "C")
+            .unwrap()
+            .unwrap();
+        assert!(
+            String::from_utf8(updated)
+                .unwrap()
+                .contains("<ns0:name>Old</ns0:name><ns0:name>C</ns0:name>")
+        );
+
+        assert!(
+            update_bound_node(xml, &binding("/ns0:form[1]/ns0:name[3]"), "C")
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            update_bound_node(xml, &binding("/ns0:form[1]/@id"), "C")
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,716 @@
+/// Insertion of document fragments at bookmarks.
+///
+/// Fragments extracted with
+/// [`Document::extract_bookmark_content`](crate::ooxml::docx::Document::extract_bookmark_content)
+/// are inserted when the package is saved. Their style, numbering,
+/// relationship, bookmark and drawing IDs are remapped so they do not clash
+/// with the destination document, and the parts they use are copied over.
+use crate::common::xml::attr;
+use crate::ooxml::docx::fragment::{
+    DocumentFragment, FragmentPart, FragmentTarget, Node, find_bookmark, has_content,
+    is_relationship_attr, parse_tree,
+};
+use crate::ooxml::error::{OoxmlError, Result};
+use crate::ooxml::opc::OpcPackage;
+use crate::ooxml::opc::constants::{content_type as ct, relationship_type as rt};
+use crate::ooxml::opc::packuri::PackURI;
+use crate::ooxml::opc::part::{BlobPart, Part};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::{Reader, Writer};
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+/// Where a fragment goes relative to the target bookmark.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FragmentPosition {
+    /// Before the start of the bookmark
+    Start,
+    /// After the end of the bookmark
+    End,
+    /// In place of the bookmarked content, inside the bookmark
+    ///
+    /// The bookmark has to start and end within the same paragraph, or
+    /// between the same blocks.
+    Replace,
+}
+
+/// A fragment waiting to be inserted when the package is saved.
+#[derive(Debug, Clone)]
+pub(crate) struct FragmentInsertion {
+    pub(crate) bookmark: String,
+    pub(crate) fragment: DocumentFragment,
+    pub(crate) position: FragmentPosition,
+}
+
+/// WordprocessingML namespace, for parts created from scratch.
+const W_NS: &str = "http://schemas.openxmlformats.org/wordprocessingml/2006/main";
+
+/// ID replacements applied to fragment XML.
+#[derive(Default)]
+struct Remap {
+    styles: HashMap<String, String>,
+    nums: HashMap<String, String>,
+    abstract_nums: HashMap<String, String>,
+    relationships: HashMap<String, String>,
+    /// Added to bookmark IDs
+    bookmark_offset: u64,
+    /// Bookmark IDs left out
+    dropped_bookmarks: HashSet<String>,
+    /// Added to drawing object IDs
+    drawing_offset: u64,
+}
+
+/// Insert a fragment into the document part `doc_uri` of `opc`.
+pub(crate) fn insert_fragment(
+    opc: &mut OpcPackage,
+    doc_uri: &PackURI,
+    insertion: &FragmentInsertion,
+) -> Result<()> {
+    let fragment = &insertion.fragment;
+    let doc_xml = opc.get_part(doc_uri)?.try_blob()?.to_vec();
+    let root = parse_tree(&doc_xml)?;
+    let (start, end) = find_bookmark(&doc_xml, &root, &insertion.bookmark)?;
+
+    let mut remap = Remap::default();
+    merge_numbering(opc, doc_uri, fragment, &mut remap)?;
+    merge_styles(opc, doc_uri, fragment, &mut remap)?;
+    copy_relationships(opc, doc_uri, fragment, &mut remap)?;
+
+    // Bookmark and drawing IDs continue after the largest ones in use
+    let mut bookmark_names = HashSet::new();
+    root.walk(&mut |node| match node.local() {
+        b"bookmarkStart" | b"bookmarkEnd" => {
+            remap.bookmark_offset = remap
+                .bookmark_offset
+                .max(numeric_attr(&doc_xml, node, b"id") + 1);
+            if let Some(name) = node.attr(&doc_xml, b"name") {
+                bookmark_names.insert(name);
+            }
+        },
+        b"docPr" => {
+            remap.drawing_offset = remap
+                .drawing_offset
+                .max(numeric_attr(&doc_xml, node, b"id"));
+        },
+        _ => {},
+    });
+    remap.dropped_bookmarks = unusable_bookmarks(fragment.xml.as_bytes(), &bookmark_names)?;
+    let content = rewrite(fragment.xml.as_bytes(), &remap)?;
+
+    let edits = placement(&doc_xml, &root, &start, &end, content, insertion)?;
+    let xml = declare_namespaces(&splice(&doc_xml, edits), &fragment.namespaces)?;
+    opc.get_part_mut(doc_uri)?.set_blob(xml);
+    Ok(())
+}
+
+/// Get the edits that put `content` at the bookmark.
+fn placement(
+    xml: &[u8],
+    root: &Node,
+    start: &[usize],
+    end: &[usize],
+    content: Vec<u8>,
+    insertion: &FragmentInsertion,
+) -> Result<Vec<(Range<usize>, Vec<u8>)>> {
+    let (anchor, offset, tail) = match insertion.position {
+        FragmentPosition::Start => {
+            let range = root.at(start).range.clone();
+            (start, range.start, range.start)
+        },
+        FragmentPosition::End => {
+            let range = root.at(end).range.clone();
+            (end, range.end, range.end)
+        },
+        FragmentPosition::Replace => {
+            if start[..start.len() - 1] != end[..end.len() - 1] {
+                return Err(OoxmlError::InvalidFormat(format!(
+                    "Bookmark '{}' spans several paragraphs and cannot be replaced",
+                    insertion.bookmark
+                )));
+            }
+            (start, root.at(start).range.end, root.at(end).range.start)
+        },
+    };
+    let parent = root.at(&anchor[..anchor.len() - 1]);
+
+    if parent.is_block_container() {
+        let content = if insertion.fragment.inline {
+            [b"<w:p>".as_slice(), &content, b"</w:p>"].concat()
+        } else {
+            content
+        };
+        return Ok(vec![(offset..tail, content)]);
+    }
+    if insertion.fragment.inline {
+        if matches!(parent.local(), b"tbl" | b"tr") {
+            return Err(OoxmlError::InvalidFormat(format!(
+                "Bookmark '{}' is not inside a paragraph or between blocks",
+                insertion.bookmark
+            )));
+        }
+        return Ok(vec![(offset..tail, content)]);
+    }
+    if parent.local() != b"p" {
+        return Err(OoxmlError::InvalidFormat(format!(
+            "Block content cannot be inserted at bookmark '{}' inside a {}",
+            insertion.bookmark,
+            String::from_utf8_lossy(parent.local())
+        )));
+    }
+
+    // Block content splits the paragraph holding the bookmark
+    let before: Vec<&Node> = parent
+        .children
+        .iter()
+        .filter(|child| child.range.end <= offset)
+        .collect();
+    let after: Vec<&Node> = parent
+        .children
+        .iter()
+        .filter(|child| child.range.start >= tail)
+        .collect();
+    let content_in = |nodes: &[&Node]| {
+        nodes
+            .iter()
+            .any(|node| has_content(std::slice::from_ref(*node)))
+    };
+    if !content_in(&before) {
+        return Ok(vec![
+            (parent.range.start..parent.range.start, content),
+            (offset..tail, Vec::new()),
+        ]);
+    }
+    if !content_in(&after) {
+        return Ok(vec![
+            (offset..tail, Vec::new()),
+            (parent.range.end..parent.range.end, content),
+        ]);
+    }
+
+    // The section break of the paragraph stays with its second half
+    let properties = parent
+        .children
+        .first()
+        .filter(|child| child.local() == b"pPr");
+    let mut replacement = Vec::new();
+    let section = properties.and_then(|p| p.children.iter().find(|c| c.local() == b"sectPr"));
+    match section {
+        Some(section) => {
+            replacement.extend_from_slice(&xml[parent.range.start..section.range.start]);
+            replacement.extend_from_slice(&xml[section.range.end..offset]);
+        },
+        None => replacement.extend_from_slice(&xml[parent.range.start..offset]),
+    }
+    replacement.extend_from_slice(b"</");
+    replacement.extend_from_slice(&parent.name);
+    replacement.push(b'>');
+    replacement.extend_from_slice(&content);
+    replacement.extend_from_slice(&rewrite(&xml[parent.start_tag.clone()], &Remap::default())?);
+    if let Some(properties) = properties {
+        replacement.extend_from_slice(&xml[properties.range.clone()]);
+    }
+    replacement.extend_from_slice(&xml[tail..parent.range.end]);
+    Ok(vec![(parent.range.clone(), replacement)])
+}
+
+/// Add the fragment's numbering definitions under new IDs.
+fn merge_numbering(
+    opc: &mut OpcPackage,
+    doc_uri: &PackURI,
+    fragment: &DocumentFragment,
+    remap: &mut Remap,
+) -> Result<()> {
+    if fragment.nums.is_empty() {
+        return Ok(());
+    }
+    let uri = related_part(
+        opc,
+        doc_uri,
+        rt::NUMBERING,
+        "/word/numbering.xml",
+        ct::WML_NUMBERING,
+        "numbering",
+    )?;
+    let xml = opc.get_part(&uri)?.try_blob()?.to_vec();
+    let root = parse_tree(&xml)?;
+    let mut next_abstract = 0;
+    let mut next_num = 1;
+    for child in &root.children {
+        match child.local() {
+            b"abstractNum" => {
+                next_abstract = next_abstract.max(numeric_attr(&xml, child, b"abstractNumId") + 1)
+            },
+            b"num" => next_num = next_num.max(numeric_attr(&xml, child, b"numId") + 1),
+            _ => {},
+        }
+    }
+    for (offset, abstract_num) in fragment.abstract_nums.iter().enumerate() {
+        let id = (next_abstract + offset as u64).to_string();
+        remap.abstract_nums.insert(abstract_num.id.clone(), id);
+    }
+    for (offset, num) in fragment.nums.iter().enumerate() {
+        let id = (next_num + offset as u64).to_string();
+        remap.nums.insert(num.id.clone(), id);
+    }
+
+    // Abstract definitions come before all numbering instances
+    let mut abstract_nums = Vec::new();
+    for abstract_num in &fragment.abstract_nums {
+        abstract_nums.extend(rewrite(abstract_num.xml.as_bytes(), remap)?);
+    }
+    let mut nums = Vec::new();
+    for num in &fragment.nums {
+        nums.extend(rewrite(num.xml.as_bytes(), remap)?);
+    }
+    let closing = root.range.end - (root.name.len() + 3);
+    let first_num = root
+        .children
+        .iter()
+        .find(|child| matches!(child.local(), b"num" | b"numIdMacAtCleanup"))
+        .map_or(closing, |child| child.range.start);
+    let cleanup = root
+        .children
+        .iter()
+        .find(|child| child.local() == b"numIdMacAtCleanup")
+        .map_or(closing, |child| child.range.start);
+    let xml = splice(
+        &xml,
+        vec![
+            (first_num..first_num, abstract_nums),
+            (cleanup..cleanup, nums),
+        ],
+    );
+    opc.get_part_mut(&uri)?.set_blob(xml);
+    Ok(())
+}
+
+/// Map the fragment's styles onto the destination styles, adding the
+/// missing ones.
+///
+/// A destination style with the same name wins unless the fragment forces
+/// its own definitions, which then replace the destination ones.
+fn merge_styles(
+    opc: &mut OpcPackage,
+    doc_uri: &PackURI,
+    fragment: &DocumentFragment,
+    remap: &mut Remap,
+) -> Result<()> {
+    if fragment.styles.is_empty() {
+        return Ok(());
+    }
+    let uri = related_part(
+        opc,
+        doc_uri,
+        rt::STYLES,
+        "/word/styles.xml",
+        ct::WML_STYLES,
+        "styles",
+    )?;
+    let xml = opc.get_part(&uri)?.try_blob()?.to_vec();
+    let root = parse_tree(&xml)?;
+    let mut by_id = HashMap::new();
+    let mut by_name = HashMap::new();
+    for node in root.children.iter().filter(|node| node.local() == b"style") {
+        if let Some(id) = node.attr(&xml, b"styleId") {
+            if let Some(name) = node.child_val(&xml, b"name") {
+                by_name.insert(name.to_lowercase(), id.clone());
+            }
+            by_id.insert(id, node);
+        }
+    }
+
+    // Decide on the destination ID of every style first, so that basedOn
+    // and link references can be remapped
+    enum Action {
+        Keep,
+        Replace(Range<usize>),
+        Add,
+    }
+    let mut actions = Vec::new();
+    let mut taken: HashSet<String> = by_id.keys().cloned().collect();
+    for style in &fragment.styles {
+        let existing = style
+            .name
+            .as_ref()
+            .and_then(|name| by_name.get(&name.to_lowercase()));
+        let (id, action) = match existing {
+            Some(id) if fragment.force_styles => {
+                (id.clone(), Action::Replace(by_id[id].range.clone()))
+            },
+            Some(id) => (id.clone(), Action::Keep),
+            None => {
+                // Same ID, different style: import under a fresh ID
+                let mut id = style.id.clone();
+                let mut n = 1;
+                while taken.contains(&id) {
+                    n += 1;
+                    id = format!("{}{}", style.id, n);
+                }
+                taken.insert(id.clone());
+                (id, Action::Add)
+            },
+        };
+        remap.styles.insert(style.id.clone(), id);
+        actions.push(action);
+    }
+
+    let closing = root.range.end - (root.name.len() + 3);
+    let mut edits = Vec::new();
+    for (style, action) in fragment.styles.iter().zip(actions) {
+        match action {
+            Action::Keep => {},
+            Action::Replace(range) => edits.push((range, rewrite(style.xml.as_bytes(), remap)?)),
+            Action::Add => edits.push((closing..closing, rewrite(style.xml.as_bytes(), remap)?)),
+        }
+    }
+    let xml = splice(&xml, edits);
+    opc.get_part_mut(&uri)?.set_blob(xml);
+    Ok(())
+}
+
+/// Relate the document part to the fragment's relationship targets,
+/// copying its parts into the package.
+fn copy_relationships(
+    opc: &mut OpcPackage,
+    doc_uri: &PackURI,
+    fragment: &DocumentFragment,
+    remap: &mut Remap,
+) -> Result<()> {
+    let mut added = HashMap::new();
+    for rel in &fragment.relationships {
+        let r_id = match &rel.target {
+            FragmentTarget::External(url) => {
+                opc.get_part_mut(doc_uri)?.relate_to_ext(url, &rel.reltype)
+            },
+            FragmentTarget::Part(index) => {
+                let partname = add_part(opc, &fragment.parts, *index, &mut added)?;
+                let target = partname.relative_ref(doc_uri.base_uri());
+                opc.get_part_mut(doc_uri)?.relate_to(&target, &rel.reltype)
+            },
+        };
+        remap.relationships.insert(rel.r_id.clone(), r_id);
+    }
+    Ok(())
+}
+
+/// Add a copy of a fragment part and the parts it relates to, returning
+/// its partname in the destination package.
+fn add_part(
+    opc: &mut OpcPackage,
+    parts: &[FragmentPart],
+    index: usize,
+    added: &mut HashMap<usize, PackURI>,
+) -> Result<PackURI> {
+    if let Some(partname) = added.get(&index) {
+        return Ok(partname.clone());
+    }
+    let source = &parts[index];
+    let partname = if opc.contains_part(&source.partname) {
+        let filename = source.partname.filename();
+        let stem = filename
+            .rsplit_once('.')
+            .map_or(filename, |(stem, _)| stem)
+            .trim_end_matches(|c: char| c.is_ascii_digit());
+        let base = source.partname.base_uri().trim_end_matches('/');
+        let template = match source.partname.ext() {
+            "" => format!("{}/{}%d", base, stem),
+            ext => format!("{}/{}%d.{}", base, stem, ext),
+        };
+        opc.next_partname(&template)?
+    } else {
+        source.partname.clone()
+    };
+
+    // Claim the partname before following relationships, which may lead back
+    let mut part = BlobPart::new(
+        partname.clone(),
+        source.content_type.clone(),
+        source.blob.clone(),
+    );
+    opc.add_part(Box::new(BlobPart::new(
+        partname.clone(),
+        source.content_type.clone(),
+        Vec::new(),
+    )));
+    added.insert(index, partname.clone());
+    for rel in &source.relationships {
+        let (target, external) = match &rel.target {
+            FragmentTarget::External(url) => (url.clone(), true),
+            FragmentTarget::Part(index) => {
+                let target = add_part(opc, parts, *index, added)?;
+                (target.relative_ref(partname.base_uri()), false)
+            },
+        };
+        // The part content refers to the original relationship IDs
+        part.rels_mut()
+            .add_relationship(rel.reltype.clone(), target, rel.r_id.clone(), external);
+    }
+    opc.add_part(Box::new(part));
+    Ok(partname)
+}
+
+/// Get the partname of the part related to the document part with
+/// `reltype`, creating an empty `root` part at `default` when there is none.
+pub(crate) fn related_part(
+    opc: &mut OpcPackage,
+    doc_uri: &PackURI,
+    reltype: &str,
+    default: &str,
+    content_type: &str,
+    root: &str,
+) -> Result<PackURI> {
+    if let Ok(rel) = opc.get_part(doc_uri)?.rels().part_with_reltype(reltype) {
+        return Ok(rel.target_partname()?);
+    }
+    let uri = PackURI::new(default).map_err(OoxmlError::InvalidUri)?;
+    let xml = format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?><w:{root} xmlns:w="{W_NS}"></w:{root}>"#
+    );
+    opc.add_part(Box::new(BlobPart::new(
+        uri.clone(),
+        content_type.to_string(),
+        xml.into_bytes(),
+    )));
+    let target = uri.relative_ref(doc_uri.base_uri());
+    opc.get_part_mut(doc_uri)?.relate_to(&target, reltype);
+    Ok(uri)
+}
+
+/// Get the IDs of fragment bookmarks to leave out: those missing their
+/// start or end, and those named like a destination bookmark.
+fn unusable_bookmarks(xml: &[u8], names: &HashSet<String>) -> Result<HashSet<String>> {
+    let mut starts = HashMap::new();
+    let mut ends = HashSet::new();
+    let mut reader = Reader::from_reader(xml);
+    loop {
+        match reader
+            .read_event()
+            .map_err(|e| OoxmlError::Xml(e.to_string()))?
+        {
+            Event::Start(e) | Event::Empty(e) => {
+                let Some(id) = attr(&e, b"id") else {
+                    continue;
+                };
+                match e.local_name().as_ref() {
+                    b"bookmarkStart" => {
+                        starts.insert(id, attr(&e, b"name").unwrap_or_default());
+                    },
+                    b"bookmarkEnd" => {
+                        ends.insert(id);
+                    },
+                    _ => {},
+                }
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+    let mut dropped: HashSet<String> = ends
+        .iter()
+        .filter(|id| !starts.contains_key(*id))
+        .cloned()
+        .collect();
+    dropped.extend(
+        starts
+            .into_iter()
+            .filter(|(id, name)| !ends.contains(id) || names.contains(name))
+            .map(|(id, _)| id),
+    );
+    Ok(dropped)
+}
+
+/// Rewrite the IDs in fragment XML.
+fn rewrite(xml: &[u8], remap: &Remap) -> Result<Vec<u8>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut writer = Writer::new(Vec::with_capacity(xml.len()));
+    let mut skipping = 0usize;
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        match event {
+            Event::Eof => break,
+            Event::Start(_) if skipping > 0 => skipping += 1,
+            Event::End(_) if skipping > 0 => skipping -= 1,
+            _ if skipping > 0 => {},
+            Event::Start(e) => match rewrite_start(&e, remap)? {
+                Some(start) => write(&mut writer, Event::Start(start))?,
+                None => skipping = 1,
+            },
+            Event::Empty(e) => {
+                if let Some(start) = rewrite_start(&e, remap)? {
+                    write(&mut writer, Event::Empty(start))?;
+                }
+            },
+            event => write(&mut writer, event)?,
+        }
+    }
+    Ok(writer.into_inner())
+}
+
+fn write(writer: &mut Writer<Vec<u8>>, event: Event) -> Result<()> {
+    writer
+        .write_event(event)
+        .map_err(|e| OoxmlError::Xml(e.to_string()))
+}
+
+/// Rewrite the IDs of a start tag, or get `None` for elements to leave out.
+fn rewrite_start(e: &BytesStart, remap: &Remap) -> Result<Option<BytesStart<'static>>> {
+    let element = e.local_name().as_ref().to_vec();
+    let name = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+    let mut out = BytesStart::new(name);
+    for attr in e.attributes() {
+        let attr = attr.map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        let key = attr.key.local_name().as_ref().to_vec();
+        let value = String::from_utf8_lossy(&attr.value).into_owned();
+        let mapped = match (element.as_slice(), key.as_slice()) {
+            (b"nsid", _) => return Ok(None),
+            (b"p", b"paraId" | b"textId") => continue,
+            (b"bookmarkStart" | b"bookmarkEnd", b"id") => {
+                if remap.dropped_bookmarks.contains(&value) {
+                    return Ok(None);
+                }
+                value
+                    .parse::<u64>()
+                    .ok()
+                    .map(|id| (id + remap.bookmark_offset).to_string())
+            },
+            (b"docPr", b"id") => value
+                .parse::<u64>()
+                .ok()
+                .map(|id| (id + remap.drawing_offset).to_string()),
+            (b"pStyle" | b"rStyle" | b"tblStyle" | b"basedOn" | b"link" | b"next", b"val")
+            | (b"style", b"styleId") => remap.styles.get(&value).cloned(),
+            (b"numId", b"val") | (b"num", b"numId") => remap.nums.get(&value).cloned(),
+            (b"abstractNumId", b"val") | (b"abstractNum", b"abstractNumId") => {
+                remap.abstract_nums.get(&value).cloned()
+            },
+            _ if is_relationship_attr(attr.key.as_ref()) => {
+                remap.relationships.get(&value).cloned()
+            },
+            _ => None,
+        };
+        match mapped {
+            Some(value) => {
+                let key = String::from_utf8_lossy(attr.key.as_ref()).into_owned();
+                out.push_attribute((key.as_str(), value.as_str()));
+            },
+            None => out.push_attribute(attr),
+        }
+    }
+    Ok(Some(out))
+}
+
+/// Declare the fragment namespaces missing from the root element of `xml`.
+fn declare_namespaces(xml: &[u8], namespaces: &[(String, String)]) -> Result<Vec<u8>> {
+    let root = parse_tree(xml)?;
+    let mut reader = Reader::from_reader(&xml[root.start_tag.clone()]);
+    let declared: HashSet<Vec<u8>> = match reader
+        .read_event()
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?
+    {
+        Event::Start(e) => e
+            .attributes()
+            .flatten()
+            .map(|attr| attr.key.as_ref().to_vec())
+            .collect(),
+        _ => return Ok(xml.to_vec()),
+    };
+    let mut declarations = String::new();
+    for (prefix, uri) in namespaces {
+        if !declared.contains(format!("xmlns:{}", prefix).as_bytes()) {
+            declarations.push_str(&format!(" xmlns:{}=\"{}\"", prefix, uri));
+        }
+    }
+    let close = root.start_tag.end - 1;
+    Ok(splice(xml, vec![(close..close, declarations.into_bytes())]))
+}
+
+/// Apply non-overlapping edits, sorted by position, to `xml`.
+pub(crate) fn splice(xml: &[u8], mut edits: Vec<(Range<usize>, Vec<u8>)>) -> Vec<u8> {
+    edits.sort_by_key(|(range, _)| range.start);
+    let mut out = Vec::with_capacity(xml.len());
+    let mut pos = 0;
+    for (range, replacement) in edits {
+        if range.start < pos {
+            continue;
+        }
+        out.extend_from_slice(&xml[pos..range.start]);
+        out.extend_from_slice(&replacement);
+        pos = range.end;
+    }
+    out.extend_from_slice(&xml[pos..]);
+    out
+}
+
+/// Get a numeric attribute of a node, or zero.
+fn numeric_attr(xml: &[u8], node: &Node, name: &[u8]) -> u64 {
+    node.attr(xml, name)
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_ids() {
+        let mut remap = Remap {
+            bookmark_offset: 10,
+            drawing_offset: 5,
+            ..Default::default()
+        };
+        remap
+            .styles
+            .insert("Quote".to_string(), "Quote2".to_string());
+        remap.nums.insert("1".to_string(), "7".to_string());
+        remap
+            .relationships
+            .insert("rId3".to_string(), "rId12".to_string());
+        remap.dropped_bookmarks.insert("4".to_string());
+        let xml = br#"<w:p w14:paraId="1A2B"><w:pPr><w:pStyle w:val="Quote"/><w:numPr><w:numId w:val="1"/></w:numPr></w:pPr><w:bookmarkStart w:id="2" w:name="x"/><w:bookmarkEnd w:id="4"/><w:hyperlink r:id="rId3"><w:r><w:t>a &amp; b</w:t></w:r></w:hyperlink><wp:docPr id="1" name="Picture"/><w:bookmarkEnd w:id="2"/></w:p>"#;
+        let out = String::from_utf8(rewrite(xml, &remap).unwrap()).unwrap();
+        assert_eq!(
+            out,
+            r#"<w:p><w:pPr><w:pStyle w:val="Quote2"/><w:numPr><w:numId w:val="7"/></w:numPr></w:pPr><w:bookmarkStart w:id="12" w:name="x"/><w:hyperlink r:id="rId12"><w:r><w:t>a &amp; b</w:t></w:r></w:hyperlink><wp:docPr id="6" name="Picture"/><w:bookmarkEnd w:id="12"/></w:p>"#
+        );
+    }
+
+    #[test]
+    fn test_unusable_bookmarks() {
+        let xml = br#"<w:bookmarkStart w:id="1" w:name="a"/><w:bookmarkEnd w:id="1"/><w:bookmarkStart w:id="2" w:name="taken"/><w:bookmarkEnd w:id="2"/><w:bookmarkEnd w:id="3"/>"#;
+        let names = HashSet::from(["taken".to_string()]);
+        let dropped = unusable_bookmarks(xml, &names).unwrap();
+        assert_eq!(dropped, HashSet::from(["2".to_string(), "3".to_string()]));
+    }
+
+    #[test]
+    fn test_split_paragraph_for_blocks() {
+        let xml = br#"<w:body><w:p w14:paraId="01"><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:t>left</w:t></w:r><w:bookmarkStart w:id="0" w:name="here"/><w:bookmarkEnd w:id="0"/><w:r><w:t>right</w:t></w:r></w:p></w:body>"#;
+        let root = parse_tree(xml).unwrap();
+        let (start, end) = find_bookmark(xml, &root, "here").unwrap();
+        let insertion = FragmentInsertion {
+            bookmark: "here".to_string(),
+            fragment: DocumentFragment {
+                xml: String::new(),
+                inline: false,
+                namespaces: Vec::new(),
+                styles: Vec::new(),
+                abstract_nums: Vec::new(),
+                nums: Vec::new(),
+                relationships: Vec::new(),
+                parts: Vec::new(),
+                force_styles: false,
+            },
+            position: FragmentPosition::Start,
+        };
+        let content = b"<w:tbl/>".to_vec();
+        let edits = placement(xml, &root, &start, &end, content, &insertion).unwrap();
+        let out = String::from_utf8(splice(xml, edits)).unwrap();
+        assert_eq!(
+            out,
+            r#"<w:body><w:p w14:paraId="01"><w:pPr><w:jc w:val="center"/></w:pPr><w:r><w:t>left</w:t></w:r></w:p><w:tbl/><w:p><w:pPr><w:jc w:val="center"/></w:pPr><w:bookmarkStart w:id="0" w:name="here"/><w:bookmarkEnd w:id="0"/><w:r><w:t>right</w:t></w:r></w:p></w:body>"#
+        );
+    }
+}
@@ -0,0 +1,1060 @@
+//! Field evaluation for mail merge and archival output.
+//!
+//! Word stores a field as its instruction (`MERGEFIELD Name \* Upper`)
+//! followed by the result it last computed. [`FieldContext`] supplies the
+//! data to compute fresh results from:
+//!
+//! - `MERGEFIELD name` takes the merge data value, with `\b`/`\f` text
+//!   around non-empty values;
+//! - `DOCPROPERTY name` takes a document property;
+//! - `DATE` and `TIME` take the current time, formatted with `\@`;
+//! - `SEQ id` counts (`\c` repeats, `\r n` resets, `\h` hides);
+//! - `IF left op right "then" "else"` compares numbers or text (`=` and
+//!   `<>` accept `*` and `?` wildcards).
+//!
+//! Nested fields are evaluated first and their results used in the
+//! instruction of the field around them. The `\* Upper`, `Lower`, `Caps`,
+//! `FirstCap`, `Arabic`, `ALPHABETIC`, `alphabetic`, `ROMAN` and `roman`
+//! switches are applied to results; with `\* MERGEFORMAT` the new result
+//! keeps the formatting of the old one, otherwise it takes the formatting of
+//! the field code.
+//!
+//! Fields that cannot be computed (other field types, merge fields without
+//! data) keep their current result.
+//!
+//! The document XML is rewritten in place when the package is saved, so
+//! everything outside the fields is left as it was.
+
+use super::form::text_run;
+use crate::ooxml::docx::content_control::push_general_ref;
+use crate::ooxml::error::{OoxmlError, Result};
+use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, Timelike};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::HashMap;
+use std::ops::Range;
+
+/// Data used to compute field results.
+///
+/// Names are matched case-insensitively, as Word does.
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::ooxml::docx::writer::FieldContext;
+///
+/// let context = FieldContext::new()
+///     .with_merge_field("FirstName", "Ada")
+///     .with_merge_field("City", "London")
+///     .with_property("Title", "Invitation");
+/// assert_eq!(context.merge_value("firstname"), Some("Ada"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FieldContext {
+    /// Merge data by lowercase field name
+    merge_data: HashMap<String, String>,
+    /// Document properties by lowercase name
+    properties: HashMap<String, String>,
+    /// Time used by `DATE` and `TIME` fields
+    now: Option<NaiveDateTime>,
+}
+
+impl FieldContext {
+    /// Create an empty context.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the value of a `MERGEFIELD`.
+    pub fn with_merge_field(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.merge_data.insert(name.to_lowercase(), value.into());
+        self
+    }
+
+    /// Add a record of merge data.
+    pub fn with_merge_data<K, V>(mut self, data: impl IntoIterator<Item = (K, V)>) -> Self
+    where
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        self.merge_data.extend(
+            data.into_iter()
+                .map(|(name, value)| (name.as_ref().to_lowercase(), value.into())),
+        );
+        self
+    }
+
+    /// Add the value of a document property for `DOCPROPERTY` fields.
+    ///
+    /// When saving through a package, its core, extended and custom
+    /// properties are used for names not given here.
+    pub fn with_property(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.properties.insert(name.to_lowercase(), value.into());
+        self
+    }
+
+    /// Set the time used by `DATE` and `TIME` fields instead of the clock.
+    pub fn with_now(mut self, now: NaiveDateTime) -> Self {
+        self.now = Some(now);
+        self
+    }
+
+    /// Get the value of a `MERGEFIELD`.
+    pub fn merge_value(&self, name: &str) -> Option<&str> {
+        self.merge_data
+            .get(&name.to_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Get the value of a document property.
+    pub fn property(&self, name: &str) -> Option<&str> {
+        self.properties
+            .get(&name.to_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Add a document property unless the context already has it.
+    pub(crate) fn add_default_property(&mut self, name: &str, value: String) {
+        self.properties.entry(name.to_lowercase()).or_insert(value);
+    }
+}
+
+/// A pending change to the fields of a document.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum FieldOperation {
+    /// Compute new results
+    Update(FieldContext),
+    /// Replace fields with their results
+    Flatten,
+}
+
+/// A part of an instruction: literal text or the result of a nested field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Piece {
+    Text(String),
+    Nested(String),
+}
+
+/// A parsed field instruction.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Instruction {
+    /// Field type, upper case
+    kind: String,
+    /// Arguments before and between switches
+    args: Vec<String>,
+    /// Switches with their arguments, such as `('@', Some("d MMMM yyyy"))`
+    switches: Vec<(char, Option<String>)>,
+}
+
+/// Switches followed by an argument.
+const SWITCHES_WITH_ARGUMENT: &[char] = &['@', '*', '#', 'b', 'f', 'r', 's'];
+
+impl Instruction {
+    fn parse(pieces: &[Piece]) -> Self {
+        // (token, may be a switch)
+        let mut tokens: Vec<(String, bool)> = Vec::new();
+        for piece in pieces {
+            match piece {
+                Piece::Nested(result) => tokens.push((result.clone(), false)),
+                Piece::Text(text) => tokenize(text, &mut tokens),
+            }
+        }
+
+        let mut instruction = Self::default();
+        let mut tokens = tokens.into_iter();
+        if let Some((kind, _)) = tokens.next() {
+            instruction.kind = kind.to_uppercase();
+        }
+        while let Some((token, bare)) = tokens.next() {
+            let switch = token
+                .strip_prefix('\\')
+                .filter(|_| bare)
+                .and_then(|name| name.chars().next());
+            match switch {
+                Some(switch) => {
+                    let argument = if SWITCHES_WITH_ARGUMENT.contains(&switch.to_ascii_lowercase())
+                    {
+                        tokens.next().map(|(argument, _)| argument)
+                    } else {
+                        None
+                    };
+                    instruction.switches.push((switch, argument));
+                },
+                None => instruction.args.push(token),
+            }
+        }
+        instruction
+    }
+
+    fn switch(&self, name: char) -> Option<Option<&str>> {
+        self.switches
+            .iter()
+            .find(|(switch, _)| *switch == name)
+            .map(|(_, argument)| argument.as_deref())
+    }
+
+    fn formats(&self) -> impl Iterator<Item = &str> {
+        self.switches
+            .iter()
+            .filter(|(switch, _)| *switch == '*')
+            .filter_map(|(_, argument)| argument.as_deref())
+    }
+
+    fn has_format(&self, name: &str) -> bool {
+        self.formats()
+            .any(|format| format.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Split instruction text into tokens, unquoting quoted ones.
+fn tokenize(text: &str, tokens: &mut Vec<(String, bool)>) {
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '"' {
+            chars.next();
+            let mut token = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' if matches!(chars.peek(), Some('"' | '\\')) => {
+                        token.extend(chars.next());
+                    },
+                    c => token.push(c),
+                }
+            }
+            tokens.push((token, false));
+        } else {
+            let mut token = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '"' {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+            tokens.push((token, true));
+        }
+    }
+}
+
+/// Computes field results in document order.
+struct Evaluator<'a> {
+    context: &'a FieldContext,
+    now: NaiveDateTime,
+    sequences: HashMap<String, i64>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn new(context: &'a FieldContext) -> Self {
+        Self {
+            context,
+            now: context.now.unwrap_or_else(|| Local::now().naive_local()),
+            sequences: HashMap::new(),
+        }
+    }
+
+    /// Compute the result of a field, or `None` to keep the current one.
+    fn evaluate(&mut self, instruction: &Instruction) -> Option<String> {
+        let result = match instruction.kind.as_str() {
+            "MERGEFIELD" => {
+                let value = self.context.merge_value(instruction.args.first()?)?;
+                let value = format_value(value, instruction);
+                if value.is_empty() {
+                    value
+                } else {
+                    let before = instruction.switch('b').flatten().unwrap_or_default();
+                    let after = instruction.switch('f').flatten().unwrap_or_default();
+                    format!("{before}{value}{after}")
+                }
+            },
+            "DOCPROPERTY" => {
+                let value = self.context.property(instruction.args.first()?)?;
+                format_value(value, instruction)
+            },
+            "DATE" | "TIME" => {
+                let default = if instruction.kind == "DATE" {
+                    "M/d/yyyy"
+                } else {
+                    "h:mm am/pm"
+                };
+                let picture = instruction.switch('@').flatten().unwrap_or(default);
+                format_date_time(self.now, picture)
+            },
+            "SEQ" => {
+                let counter = self
+                    .sequences
+                    .entry(instruction.args.first()?.to_lowercase())
+                    .or_default();
+                if let Some(reset) = instruction.switch('r') {
+                    *counter = reset.and_then(|n| n.parse().ok()).unwrap_or(0);
+                } else if instruction.switch('c').is_none() {
+                    *counter += 1;
+                }
+                if instruction.switch('h').is_some() {
+                    String::new()
+                } else {
+                    counter.to_string()
+                }
+            },
+            "IF" => {
+                let [left, op, right, rest @ ..] = instruction.args.as_slice() else {
+                    return None;
+                };
+                let branch = if compare(left, op, right)? { 0 } else { 1 };
+                rest.get(branch).cloned().unwrap_or_default()
+            },
+            _ => return None,
+        };
+        Some(apply_formats(result, instruction))
+    }
+}
+
+/// Apply `\@` to a date value.
+fn format_value(value: &str, instruction: &Instruction) -> String {
+    if let Some(Some(picture)) = instruction.switch('@')
+        && let Some(date) = parse_date_value(value)
+    {
+        return format_date_time(date, picture);
+    }
+    value.to_string()
+}
+
+fn parse_date_value(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim().trim_end_matches('Z');
+    NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S")
+        .ok()
+        .or_else(|| NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S").ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+}
+
+/// Apply the `\*` text and number formats.
+fn apply_formats(mut result: String, instruction: &Instruction) -> String {
+    for format in instruction.formats() {
+        let number = result.trim().parse::<i64>().ok();
+        result = match (format, number) {
+            (f, _) if f.eq_ignore_ascii_case("upper") => result.to_uppercase(),
+            (f, _) if f.eq_ignore_ascii_case("lower") => result.to_lowercase(),
+            (f, _) if f.eq_ignore_ascii_case("caps") => capitalize_words(&result),
+            (f, _) if f.eq_ignore_ascii_case("firstcap") => capitalize_words_first(&result),
+            ("ALPHABETIC", Some(n)) if n > 0 => alphabetic(n),
+            ("alphabetic", Some(n)) if n > 0 => alphabetic(n).to_lowercase(),
+            ("ROMAN", Some(n)) if n > 0 => roman(n),
+            ("roman", Some(n)) if n > 0 => roman(n).to_lowercase(),
+            (f, Some(n)) if f.eq_ignore_ascii_case("arabic") => n.to_string(),
+            _ => result,
+        };
+    }
+    result
+}
+
+fn capitalize_words(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut start = true;
+    for c in text.chars() {
+        if start {
+            out.extend(c.to_uppercase());
+        } else {
+            out.push(c);
+        }
+        start = c.is_whitespace();
+    }
+    out
+}
+
+fn capitalize_words_first(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// `1` → `A`, `27` → `AA`, as Word counts.
+fn alphabetic(n: i64) -> String {
+    let letter = (b'A' + ((n - 1) % 26) as u8) as char;
+    letter.to_string().repeat(((n - 1) / 26 + 1) as usize)
+}
+
+fn roman(mut n: i64) -> String {
+    const NUMERALS: &[(i64, &str)] = &[
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    let mut out = String::new();
+    for &(value, numeral) in NUMERALS {
+        while n >= value {
+            out.push_str(numeral);
+            n -= value;
+        }
+    }
+    out
+}
+
+/// Compare the operands of an `IF` field.
+fn compare(left: &str, op: &str, right: &str) -> Option<bool> {
+    use std::cmp::Ordering;
+
+    let ordering = match (left.trim().parse::<f64>(), right.trim().parse::<f64>()) {
+        (Ok(l), Ok(r)) => l.partial_cmp(&r)?,
+        _ if matches!(op, "=" | "<>") => {
+            let equal = wildcard_match(right, left);
+            return Some(equal == (op == "="));
+        },
+        _ => left.cmp(right),
+    };
+    Some(match op {
+        "=" => ordering == Ordering::Equal,
+        "<>" => ordering != Ordering::Equal,
+        "<" => ordering == Ordering::Less,
+        "<=" => ordering != Ordering::Greater,
+        ">" => ordering == Ordering::Greater,
+        ">=" => ordering != Ordering::Less,
+        _ => return None,
+    })
+}
+
+/// Match `text` against a pattern with `*` and `?` wildcards.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((bp, bt)) = backtrack {
+            p = bp + 1;
+            t = bt + 1;
+            backtrack = Some((bp, bt + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Format a date and time with a Word date-time picture such as
+/// `dddd, MMMM d, yyyy` or `HH:mm`.
+///
+/// `M` is the month and `m` the minute; text in single quotes is copied.
+pub(crate) fn format_date_time(value: NaiveDateTime, picture: &str) -> String {
+    let chars: Vec<char> = picture.chars().collect();
+    let mut out = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let rest: String = chars[i..].iter().take(5).collect();
+        if rest.eq_ignore_ascii_case("am/pm") {
+            let marker = if value.hour() < 12 { "am" } else { "pm" };
+            if rest.starts_with('A') {
+                out.push_str(&marker.to_uppercase());
+            } else {
+                out.push_str(marker);
+            }
+            i += 5;
+            continue;
+        }
+        let run = chars[i..].iter().take_while(|&&next| next == c).count();
+        let hour12 = match value.hour() % 12 {
+            0 => 12,
+            hour => hour,
+        };
+        match (c, run) {
+            ('\'', _) => {
+                // Quoted literal text
+                let literal: Vec<char> = chars[i + 1..]
+                    .iter()
+                    .take_while(|&&c| c != '\'')
+                    .copied()
+                    .collect();
+                out.extend(&literal);
+                i += literal.len() + 2;
+                continue;
+            },
+            ('y' | 'Y', 3..) => out.push_str(&format!("{:04}", value.year())),
+            ('y' | 'Y', _) => out.push_str(&format!("{:02}", value.year().rem_euclid(100))),
+            ('M', 4..) => out.push_str(&value.format("%B").to_string()),
+            ('M', 3) => out.push_str(&value.format("%b").to_string()),
+            ('M', 2) => out.push_str(&format!("{:02}", value.month())),
+            ('M', _) => out.push_str(&value.month().to_string()),
+            ('d' | 'D', 4..) => out.push_str(&value.format("%A").to_string()),
+            ('d' | 'D', 3) => out.push_str(&value.format("%a").to_string()),
+            ('d' | 'D', 2) => out.push_str(&format!("{:02}", value.day())),
+            ('d' | 'D', _) => out.push_str(&value.day().to_string()),
+            ('H', 2..) => out.push_str(&format!("{:02}", value.hour())),
+            ('H', _) => out.push_str(&value.hour().to_string()),
+            ('h', 2..) => out.push_str(&format!("{:02}", hour12)),
+            ('h', _) => out.push_str(&hour12.to_string()),
+            ('m', 2..) => out.push_str(&format!("{:02}", value.minute())),
+            ('m', _) => out.push_str(&value.minute().to_string()),
+            ('s' | 'S', 2..) => out.push_str(&format!("{:02}", value.second())),
+            ('s' | 'S', _) => out.push_str(&value.second().to_string()),
+            _ => {
+                out.push(c);
+                i += 1;
+                continue;
+            },
+        }
+        i += run;
+    }
+    out
+}
+
+/// A field character or instruction text inside a run.
+#[derive(Debug)]
+enum FieldChar {
+    Begin,
+    Separate,
+    End,
+    Instruction(String),
+}
+
+/// A run of the document.
+#[derive(Debug, Default)]
+struct RunToken {
+    range: Range<usize>,
+    /// Start of the element holding the run
+    parent: usize,
+    properties: Option<Range<usize>>,
+    chars: Vec<FieldChar>,
+    text: String,
+}
+
+/// The parts of the document XML fields are made of.
+#[derive(Debug)]
+enum Token {
+    Run(RunToken),
+    /// `fldSimple` start tag and instruction
+    SimpleStart(Range<usize>, String),
+    SimpleEnd(Range<usize>),
+    SimpleEmpty(Range<usize>, String),
+}
+
+/// Split document XML into runs and simple fields.
+fn tokenize_xml(xml: &[u8]) -> Result<(Vec<Token>, String)> {
+    fn instr(e: &BytesStart) -> String {
+        e.attributes()
+            .flatten()
+            .find(|attr| attr.key.local_name().as_ref() == b"instr")
+            .and_then(|attr| attr.unescape_value().ok())
+            .map(|value| value.into_owned())
+            .unwrap_or_default()
+    }
+    fn field_char(e: &BytesStart) -> Option<FieldChar> {
+        let kind = e
+            .attributes()
+            .flatten()
+            .find(|attr| attr.key.local_name().as_ref() == b"fldCharType")?;
+        match kind.value.as_ref() {
+            b"begin" => Some(FieldChar::Begin),
+            b"separate" => Some(FieldChar::Separate),
+            b"end" => Some(FieldChar::End),
+            _ => None,
+        }
+    }
+
+    let mut reader = Reader::from_reader(xml);
+    let mut tokens = Vec::new();
+    let mut prefix = None;
+    // (local name, start)
+    let mut stack: Vec<(Vec<u8>, usize)> = Vec::new();
+    let mut run: Option<RunToken> = None;
+    let mut instruction: Option<String> = None;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader
+            .read_event()
+            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        let end = reader.buffer_position() as usize;
+        let in_run = stack.last().is_some_and(|(local, _)| local == b"r");
+
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let empty = matches!(event, Event::Empty(_));
+                let local = e.local_name().as_ref().to_vec();
+                match local.as_slice() {
+                    b"r" if !empty => {
+                        if prefix.is_none() {
+                            prefix = Some(match e.name().prefix() {
+                                Some(p) => format!("{}:", String::from_utf8_lossy(p.as_ref())),
+                                None => String::new(),
+                            });
+                        }
+                        run = Some(RunToken {
+                            parent: stack.last().map_or(0, |(_, start)| *start),
+                            ..RunToken::default()
+                        });
+                    },
+                    b"rPr" if in_run && empty => {
+                        if let Some(run) = run.as_mut() {
+                            run.properties = Some(start..end);
+                        }
+                    },
+                    b"fldChar" if in_run => {
+                        if let (Some(run), Some(c)) = (run.as_mut(), field_char(e)) {
+                            run.chars.push(c);
+                        }
+                    },
+                    b"instrText" if in_run && !empty => instruction = Some(String::new()),
+                    b"tab" if in_run => run.iter_mut().for_each(|run| run.text.push('\t')),
+                    b"br" | b"cr" if in_run => run.iter_mut().for_each(|run| run.text.push('\n')),
+                    b"fldSimple" if empty => tokens.push(Token::SimpleEmpty(start..end, instr(e))),
+                    b"fldSimple" => tokens.push(Token::SimpleStart(start..end, instr(e))),
+                    _ => {},
+                }
+                if !empty {
+                    stack.push((local, start));
+                }
+            },
+            Event::End(ref e) => {
+                let (_, open) = stack.pop().unwrap_or_default();
+                match e.local_name().as_ref() {
+                    b"r" => {
+                        if let Some(mut run) = run.take() {
+                            run.range = open..end;
+                            tokens.push(Token::Run(run));
+                        }
+                    },
+                    b"rPr" if in_run_parent(&stack) => {
+                        if let Some(run) = run.as_mut() {
+                            run.properties = Some(open..end);
+                        }
+                    },
+                    b"instrText" => {
+                        if let (Some(run), Some(text)) = (run.as_mut(), instruction.take()) {
+                            run.chars.push(FieldChar::Instruction(text));
+                        }
+                    },
+                    b"fldSimple" => tokens.push(Token::SimpleEnd(start..end)),
+                    _ => {},
+                }
+            },
+            Event::Text(ref e) => {
+                let text = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                push_text(&stack, &mut run, &mut instruction, &text);
+            },
+            Event::GeneralRef(ref e) => {
+                let mut text = String::new();
+                push_general_ref(&mut text, e)?;
+                push_text(&stack, &mut run, &mut instruction, &text);
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok((tokens, prefix.unwrap_or_else(|| "w:".to_string())))
+}
+
+/// Whether the innermost open element is a run.
+fn in_run_parent(stack: &[(Vec<u8>, usize)]) -> bool {
+    stack.last().is_some_and(|(local, _)| local == b"r")
+}
+
+fn push_text(
+    stack: &[(Vec<u8>, usize)],
+    run: &mut Option<RunToken>,
+    instruction: &mut Option<String>,
+    text: &str,
+) {
+    match stack.last().map(|(local, _)| local.as_slice()) {
+        Some(b"instrText") => instruction.iter_mut().for_each(|i| i.push_str(text)),
+        Some(b"t") => run.iter_mut().for_each(|run| run.text.push_str(text)),
+        _ => {},
+    }
+}
+
+/// Where an open field starts.
+#[derive(Debug, Clone, Copy)]
+enum Start {
+    /// Token of the run with the `begin` field character, and of the run
+    /// with the `separate` one once seen
+    Complex(usize, Option<usize>),
+    /// Token of the `fldSimple` start tag
+    Simple(usize),
+}
+
+#[derive(Debug)]
+struct OpenField {
+    start: Start,
+    instruction: Vec<Piece>,
+    /// Text of the current result
+    result: String,
+    /// Properties of the first run of the current result
+    result_properties: Option<Option<Range<usize>>>,
+}
+
+impl OpenField {
+    fn in_result(&self) -> bool {
+        !matches!(self.start, Start::Complex(_, None))
+    }
+}
+
+/// Apply a field operation to document XML.
+pub(crate) fn apply_field_operation(xml: &[u8], operation: &FieldOperation) -> Result<Vec<u8>> {
+    let (tokens, prefix) = tokenize_xml(xml)?;
+    let mut evaluator = match operation {
+        FieldOperation::Update(context) => Some(Evaluator::new(context)),
+        FieldOperation::Flatten => None,
+    };
+    let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+    let mut open: Vec<OpenField> = Vec::new();
+
+    let mut close = |field: OpenField, end: usize, open: &mut Vec<OpenField>| {
+        let instruction = Instruction::parse(&field.instruction);
+        let result = evaluator
+            .as_mut()
+            .and_then(|evaluator| evaluator.evaluate(&instruction));
+        let edit = match &result {
+            Some(result) => update_edit(xml, &tokens, &field, end, &instruction, result, &prefix)
+                .into_iter()
+                .collect(),
+            None if evaluator.is_none() => flatten_edits(&tokens, &field, end),
+            None => Vec::new(),
+        };
+        edits.extend(edit);
+
+        // Hand the result to the field around this one
+        let value = result.unwrap_or(field.result);
+        if let Some(parent) = open.last_mut() {
+            if parent.in_result() {
+                parent.result.push_str(&value);
+            } else {
+                parent.instruction.push(Piece::Nested(value));
+            }
+        }
+    };
+
+    for (index, token) in tokens.iter().enumerate() {
+        match token {
+            Token::Run(run) => {
+                for c in &run.chars {
+                    match c {
+                        FieldChar::Begin => open.push(OpenField {
+                            start: Start::Complex(index, None),
+                            instruction: Vec::new(),
+                            result: String::new(),
+                            result_properties: None,
+                        }),
+                        FieldChar::Instruction(text) => {
+                            if let Some(field) = open.last_mut().filter(|f| !f.in_result()) {
+                                field.instruction.push(Piece::Text(text.clone()));
+                            }
+                        },
+                        FieldChar::Separate => {
+                            if let Some(OpenField {
+                                start: Start::Complex(_, separate @ None),
+                                ..
+                            }) = open.last_mut()
+                            {
+                                *separate = Some(index);
+                            }
+                        },
+                        FieldChar::End => {
+                            if let Some(field) =
+                                open.pop_if(|field| matches!(field.start, Start::Complex(..)))
+                            {
+                                close(field, index, &mut open);
+                            }
+                        },
+                    }
+                }
+                if run.chars.is_empty()
+                    && let Some(field) = open.last_mut().filter(|f| f.in_result())
+                {
+                    field.result.push_str(&run.text);
+                    field
+                        .result_properties
+                        .get_or_insert_with(|| run.properties.clone());
+                }
+            },
+            Token::SimpleStart(_, instruction) => open.push(OpenField {
+                start: Start::Simple(index),
+                instruction: vec![Piece::Text(instruction.clone())],
+                result: String::new(),
+                result_properties: None,
+            }),
+            Token::SimpleEnd(_) => {
+                if let Some(field) = open.pop_if(|field| matches!(field.start, Start::Simple(_))) {
+                    close(field, index, &mut open);
+                }
+            },
+            Token::SimpleEmpty(_, instruction) => {
+                let field = OpenField {
+                    start: Start::Simple(index),
+                    instruction: vec![Piece::Text(instruction.clone())],
+                    result: String::new(),
+                    result_properties: None,
+                };
+                close(field, index, &mut open);
+            },
+        }
+    }
+
+    // Outer edits first; edits inside them are dropped
+    edits.sort_by_key(|(range, _)| (range.start, std::cmp::Reverse(range.end)));
+    let mut out = Vec::with_capacity(xml.len());
+    let mut pos = 0;
+    for (range, replacement) in edits {
+        if range.start < pos {
+            continue;
+        }
+        out.extend_from_slice(&xml[pos..range.start]);
+        out.extend_from_slice(replacement.as_bytes());
+        pos = range.end;
+    }
+    out.extend_from_slice(&xml[pos..]);
+    Ok(out)
+}
+
+fn run_token(tokens: &[Token], index: usize) -> Option<&RunToken> {
+    match &tokens[index] {
+        Token::Run(run) => Some(run),
+        _ => None,
+    }
+}
+
+fn tag_range(tokens: &[Token], index: usize) -> Range<usize> {
+    match &tokens[index] {
+        Token::Run(run) => run.range.clone(),
+        Token::SimpleStart(range, _) | Token::SimpleEnd(range) | Token::SimpleEmpty(range, _) => {
+            range.clone()
+        },
+    }
+}
+
+/// The edit that puts a new result into a field.
+fn update_edit(
+    xml: &[u8],
+    tokens: &[Token],
+    field: &OpenField,
+    end: usize,
+    instruction: &Instruction,
+    result: &str,
+    prefix: &str,
+) -> Option<(Range<usize>, String)> {
+    let slice = |range: &Option<Range<usize>>| {
+        range
+            .clone()
+            .map(|range| String::from_utf8_lossy(&xml[range]).into_owned())
+    };
+    let end_range = tag_range(tokens, end);
+
+    match field.start {
+        Start::Complex(begin, separate) => {
+            let begin = run_token(tokens, begin)?;
+            let end_run = run_token(tokens, end)?;
+            let properties = match &field.result_properties {
+                Some(properties) if instruction.has_format("MERGEFORMAT") => properties,
+                _ => &begin.properties,
+            };
+            let runs = text_run(prefix, slice(properties).as_deref(), result);
+            match separate.and_then(|separate| run_token(tokens, separate)) {
+                Some(separate) if separate.parent == end_run.parent => {
+                    Some((separate.range.end..end_run.range.start, runs))
+                },
+                None if begin.parent == end_run.parent => {
+                    let separator = format!(
+                        r#"<{prefix}r>{}<{prefix}fldChar {prefix}fldCharType="separate"/></{prefix}r>"#,
+                        slice(&begin.properties).unwrap_or_default()
+                    );
+                    Some((end_run.range.start..end_run.range.start, separator + &runs))
+                },
+                _ => None,
+            }
+        },
+        Start::Simple(start) => {
+            let properties = field.result_properties.clone().flatten();
+            let runs = text_run(prefix, slice(&properties).as_deref(), result);
+            let start_range = tag_range(tokens, start);
+            if start == end {
+                // `<w:fldSimple .../>` gets content
+                let tag = String::from_utf8_lossy(&xml[start_range.clone()]);
+                let open = tag.trim_end_matches("/>").trim_end();
+                Some((start_range, format!("{open}>{runs}</{prefix}fldSimple>")))
+            } else {
+                Some((start_range.end..end_range.start, runs))
+            }
+        },
+    }
+}
+
+/// The edits that replace a field by its current result.
+fn flatten_edits(tokens: &[Token], field: &OpenField, end: usize) -> Vec<(Range<usize>, String)> {
+    let end_range = tag_range(tokens, end);
+    match field.start {
+        Start::Complex(begin, separate) => {
+            let (Some(begin), Some(end_run)) = (run_token(tokens, begin), run_token(tokens, end))
+            else {
+                return Vec::new();
+            };
+            match separate.and_then(|separate| run_token(tokens, separate)) {
+                Some(separate) if separate.parent == begin.parent => vec![
+                    (begin.range.start..separate.range.end, String::new()),
+                    (end_range, String::new()),
+                ],
+                None if begin.parent == end_run.parent => {
+                    vec![(begin.range.start..end_run.range.end, String::new())]
+                },
+                _ => Vec::new(),
+            }
+        },
+        Start::Simple(start) if start == end => vec![(end_range, String::new())],
+        Start::Simple(start) => vec![
+            (tag_range(tokens, start), String::new()),
+            (end_range, String::new()),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(instruction: &str) -> Instruction {
+        Instruction::parse(&[Piece::Text(instruction.to_string())])
+    }
+
+    fn now() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 3, 5)
+            .unwrap()
+            .and_hms_opt(14, 7, 9)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_parse_instruction() {
+        let instruction = parse(r#" MERGEFIELD  "First Name" \b "Dear " \* Upper \* MERGEFORMAT "#);
+        assert_eq!(instruction.kind, "MERGEFIELD");
+        assert_eq!(instruction.args, ["First Name"]);
+        assert_eq!(instruction.switch('b'), Some(Some("Dear ")));
+        assert_eq!(
+            instruction.formats().collect::<Vec<_>>(),
+            ["Upper", "MERGEFORMAT"]
+        );
+
+        let nested = Instruction::parse(&[
+            Piece::Text("IF ".to_string()),
+            Piece::Nested("New York".to_string()),
+            Piece::Text(r#" = "New*" "yes" "no""#.to_string()),
+        ]);
+        assert_eq!(nested.args, ["New York", "=", "New*", "yes", "no"]);
+    }
+
+    #[test]
+    fn test_evaluate_fields() {
+        let context = FieldContext::new()
+            .with_merge_field("Name", "ada lovelace")
+            .with_merge_field("Empty", "")
+            .with_merge_field("Joined", "2024-01-31")
+            .with_property("Title", "Report")
+            .with_now(now());
+        let mut evaluator = Evaluator::new(&context);
+        let mut eval = |instruction: &str| evaluator.evaluate(&parse(instruction));
+
+        assert_eq!(
+            eval(r#"MERGEFIELD name \* Caps"#).as_deref(),
+            Some("Ada Lovelace")
+        );
+        assert_eq!(
+            eval(r#"MERGEFIELD Name \* Upper"#).as_deref(),
+            Some("ADA LOVELACE")
+        );
+        assert_eq!(eval(r#"MERGEFIELD Empty \b "x""#).as_deref(), Some(""));
+        assert_eq!(eval(r#"MERGEFIELD Missing"#), None);
+        assert_eq!(
+            eval(r#"MERGEFIELD Joined \@ "d MMM yyyy""#).as_deref(),
+            Some("31 Jan 2024")
+        );
+        assert_eq!(eval("DOCPROPERTY title").as_deref(), Some("Report"));
+        assert_eq!(eval("DATE").as_deref(), Some("3/5/2024"));
+        assert_eq!(eval(r#"TIME \@ "HH:mm:ss""#).as_deref(), Some("14:07:09"));
+        assert_eq!(eval("PAGE"), None);
+
+        assert_eq!(eval("SEQ Figure").as_deref(), Some("1"));
+        assert_eq!(eval("SEQ figure").as_deref(), Some("2"));
+        assert_eq!(eval(r#"SEQ Figure \c \* ROMAN"#).as_deref(), Some("II"));
+        assert_eq!(eval(r#"SEQ Table \* alphabetic"#).as_deref(), Some("a"));
+        assert_eq!(eval(r#"SEQ Figure \r 10"#).as_deref(), Some("10"));
+        assert_eq!(eval(r#"SEQ Figure \h"#).as_deref(), Some(""));
+        assert_eq!(eval("SEQ Figure").as_deref(), Some("12"));
+
+        assert_eq!(eval(r#"IF 10 > 9 "big" "small""#).as_deref(), Some("big"));
+        assert_eq!(
+            eval(r#"IF "abc" = "a?c" "match""#).as_deref(),
+            Some("match")
+        );
+        assert_eq!(eval(r#"IF abc <> "a*" "yes" "no""#).as_deref(), Some("no"));
+        assert_eq!(eval(r#"IF 1 ~ 2 "yes" "no""#), None);
+    }
+
+    #[test]
+    fn test_format_date_time() {
+        assert_eq!(
+            format_date_time(now(), "dddd, MMMM d, yyyy h:mm AM/PM"),
+            "Tuesday, March 5, 2024 2:07 PM"
+        );
+        assert_eq!(format_date_time(now(), "yy-MM-dd'T'HH"), "24-03-05T14");
+        assert_eq!(roman(1994), "MCMXCIV");
+        assert_eq!(alphabetic(28), "BB");
+    }
+
+    const DOC: &str = r#"<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body><w:p><w:r><w:t xml:space="preserve">Dear </w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:rPr><w:b/></w:rPr><w:instrText xml:space="preserve"> MERGEFIELD Name \* MERGEFORMAT </w:instrText></w:r><w:r><w:rPr><w:b/></w:rPr><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:rPr><w:i/></w:rPr><w:t>«Name»</w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:fldChar w:fldCharType="end"/></w:r><w:r><w:t>,</w:t></w:r></w:p><w:p><w:fldSimple w:instr=" PAGE "><w:r><w:t>7</w:t></w:r></w:fldSimple><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve">IF </w:instrText></w:r><w:r><w:fldChar w:fldCharType="begin"/></w:r><w:r><w:instrText xml:space="preserve"> MERGEFIELD City </w:instrText></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r><w:r><w:instrText xml:space="preserve"> = "Paris" "bonjour" "hello"</w:instrText></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p></w:body></w:document>"#;
+
+    #[test]
+    fn test_update_and_flatten() {
+        let context = FieldContext::new()
+            .with_merge_field("Name", "Ada & co")
+            .with_merge_field("City", "Paris");
+        let updated =
+            apply_field_operation(DOC.as_bytes(), &FieldOperation::Update(context)).unwrap();
+        let text = String::from_utf8(updated.clone()).unwrap();
+
+        // MERGEFORMAT keeps the formatting of the old result
+        assert!(text.contains(
+            r#"<w:fldChar w:fldCharType="separate"/></w:r><w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve">Ada &amp; co</w:t></w:r><w:r><w:rPr><w:b/></w:rPr><w:fldChar w:fldCharType="end"/>"#
+        ));
+        // A result is added to the IF field, which had none
+        assert!(text.contains(
+            r#"<w:r><w:fldChar w:fldCharType="separate"/></w:r><w:r><w:t xml:space="preserve">bonjour</w:t></w:r><w:r><w:fldChar w:fldCharType="end"/></w:r></w:p>"#
+        ));
+        // PAGE cannot be computed and keeps its result
+        assert!(
+            text.contains(r#"<w:fldSimple w:instr=" PAGE "><w:r><w:t>7</w:t></w:r></w:fldSimple>"#)
+        );
+
+        let flat = apply_field_operation(&updated, &FieldOperation::Flatten).unwrap();
+        let flat = String::from_utf8(flat).unwrap();
+        assert!(!flat.contains("fldChar"));
+        assert!(!flat.contains("instrText"));
+        assert!(!flat.contains("fldSimple"));
+        assert!(flat.contains(
+            r#"<w:p><w:r><w:t xml:space="preserve">Dear </w:t></w:r><w:r><w:rPr><w:i/></w:rPr><w:t xml:space="preserve">Ada &amp; co</w:t></w:r><w:r><w:t>,</w:t></w:r></w:p>"#
+        ));
+        assert!(flat.contains(
+            r#"<w:p><w:r><w:t>7</w:t></w:r><w:r><w:t xml:space="preserve">bonjour</w:t></w:r></w:p>"#
+        ));
+    }
+}
@@ -7,11 +7,15 @@ pub mod comment;
 pub mod content_control;
 pub mod doc;
 pub mod field;
+pub mod form;
+pub mod fragment;
 pub mod hyperlink;
 pub mod image;
+pub mod merge;
 pub mod note;
 pub mod paragraph;
 pub(crate) mod relmap;
+pub mod replace;
 pub mod run;
 pub mod section;
 pub mod style;
@@ -38,6 +42,9 @@ pub use image::{ImageFormat, MutableInlineImage};
 // Re-export paragraph types
 pub use paragraph::{ListType, MutableParagraph};
 
+// Re-export replace types
+pub use replace::ReplaceOptions;
+
 // Re-export run types
 pub use run::{MutableRun, RunContent};
 
@@ -57,7 +64,13 @@ pub use field::MutableField;
 pub use style::{MutableStyle, generate_styles_xml};
 
 // Re-export content control types
-pub use content_control::{ContentControlType, MutableContentControl};
+pub use content_control::{ContentControlType, ContentControlValue, MutableContentControl};
+
+// Re-export field evaluation types
+pub use merge::FieldContext;
+
+// Re-export fragment insertion types
+pub use fragment::FragmentPosition;
 
 // Re-export theme types
 pub use theme::{ColorScheme, MutableTheme};
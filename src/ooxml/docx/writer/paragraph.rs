@@ -10,7 +10,9 @@ use super::bookmark::MutableBookmark;
 use super::field::MutableField;
 use super::hyperlink::MutableHyperlink;
 use super::image::MutableInlineImage;
+use super::replace::{self, ReplaceOptions};
 use super::run::MutableRun;
+use super::section::PageNumberFormat;
 
 /// Elements that can appear in a paragraph.
 #[derive(Debug)]
@@ -37,8 +39,17 @@ pub struct MutableParagraph {
     pub(crate) properties: ParagraphProperties,
 }
 
+impl Default for MutableParagraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MutableParagraph {
-    pub(crate) fn new() -> Self {
+    /// Create an empty paragraph, for use with
+    /// [`MutableDocument::set_header`](super::MutableDocument::set_header) and
+    /// similar methods taking paragraphs.
+    pub fn new() -> Self {
         Self {
             elements: Vec::new(),
             style: None,
@@ -72,6 +83,22 @@ impl MutableParagraph {
         run
     }
 
+    /// Add a `PAGE` field showing the current page number.
+    pub fn add_page_number_field(&mut self, format: PageNumberFormat) -> &mut MutableRun {
+        let run = self.add_run();
+        run.add_page_number(format);
+        run
+    }
+
+    /// Add "Page X of Y", made of a `PAGE` and a `NUMPAGES` field.
+    pub fn add_page_x_of_y(&mut self, format: PageNumberFormat) -> &mut Self {
+        self.add_run_with_text("Page ");
+        self.add_page_number_field(format);
+        self.add_run_with_text(" of ");
+        self.add_run().add_page_count();
+        self
+    }
+
     /// Add a hyperlink to the paragraph.
     ///
     /// # Arguments
@@ -243,6 +270,30 @@ impl MutableParagraph {
         self.elements.len()
     }
 
+    /// Replace every occurrence of `old` in the paragraph text with `new`.
+    ///
+    /// Matches may span several runs; the replacement keeps the formatting of
+    /// the first run of each match. See [`MutableDocument::replace_text`] for
+    /// the details.
+    ///
+    /// Returns the number of replacements made.
+    ///
+    /// [`MutableDocument::replace_text`]: super::MutableDocument::replace_text
+    pub fn replace_text(&mut self, old: &str, new: &str) -> usize {
+        self.replace_with(
+            &|text| replace::literal_edits(text, old, new),
+            &ReplaceOptions::new(),
+        )
+    }
+
+    pub(crate) fn replace_with(
+        &mut self,
+        find: &replace::Finder<'_>,
+        options: &ReplaceOptions,
+    ) -> usize {
+        replace::replace_in_paragraph(&mut self.elements, find, options)
+    }
+
     /// Clear all elements from the paragraph.
     pub fn clear(&mut self) {
         self.elements.clear();
@@ -260,11 +311,6 @@ impl MutableParagraph {
                     .map_err(|e| OoxmlError::Xml(e.to_string()))?;
             }
 
-            if let Some(alignment) = self.properties.alignment {
-                write!(xml, "<w:jc w:val=\"{}\"/>", alignment.as_str())
-                    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-            }
-
             // Write numbering properties for lists
             if let Some(ref numbering) = self.properties.numbering {
                 xml.push_str("<w:numPr>");
@@ -275,6 +321,24 @@ impl MutableParagraph {
                 xml.push_str("</w:numPr>");
             }
 
+            // Write tab stops
+            if !self.properties.tab_stops.is_empty() {
+                xml.push_str("<w:tabs>");
+                for tab_stop in &self.properties.tab_stops {
+                    xml.push_str("<w:tab");
+                    write!(xml, " w:val=\"{}\"", escape_xml(&tab_stop.alignment))
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    write!(xml, " w:pos=\"{}\"", tab_stop.position)
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    if let Some(ref leader) = tab_stop.leader {
+                        write!(xml, " w:leader=\"{}\"", escape_xml(leader))
+                            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    }
+                    xml.push_str("/>");
+                }
+                xml.push_str("</w:tabs>");
+            }
+
             // Write spacing
             if self.properties.space_before.is_some()
                 || self.properties.space_after.is_some()
@@ -349,22 +413,10 @@ impl MutableParagraph {
                 xml.push_str("/>");
             }
 
-            // Write tab stops
-            if !self.properties.tab_stops.is_empty() {
-                xml.push_str("<w:tabs>");
-                for tab_stop in &self.properties.tab_stops {
-                    xml.push_str("<w:tab");
-                    write!(xml, " w:val=\"{}\"", escape_xml(&tab_stop.alignment))
-                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                    write!(xml, " w:pos=\"{}\"", tab_stop.position)
-                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                    if let Some(ref leader) = tab_stop.leader {
-                        write!(xml, " w:leader=\"{}\"", escape_xml(leader))
-                            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                    }
-                    xml.push_str("/>");
-                }
-                xml.push_str("</w:tabs>");
+            // Write alignment (last of the supported properties in schema order)
+            if let Some(alignment) = self.properties.alignment {
+                write!(xml, "<w:jc w:val=\"{}\"/>", alignment.as_str())
+                    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
             }
 
             xml.push_str("</w:pPr>");
@@ -432,11 +484,6 @@ impl MutableParagraph {
                     .map_err(|e| OoxmlError::Xml(e.to_string()))?;
             }
 
-            if let Some(alignment) = self.properties.alignment {
-                write!(xml, "<w:jc w:val=\"{}\"/>", alignment.as_str())
-                    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-            }
-
             // Write numbering properties for lists
             if let Some(ref numbering) = self.properties.numbering {
                 xml.push_str("<w:numPr>");
@@ -447,6 +494,24 @@ impl MutableParagraph {
                 xml.push_str("</w:numPr>");
             }
 
+            // Write tab stops
+            if !self.properties.tab_stops.is_empty() {
+                xml.push_str("<w:tabs>");
+                for tab_stop in &self.properties.tab_stops {
+                    xml.push_str("<w:tab");
+                    write!(xml, " w:val=\"{}\"", escape_xml(&tab_stop.alignment))
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    write!(xml, " w:pos=\"{}\"", tab_stop.position)
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    if let Some(ref leader) = tab_stop.leader {
+                        write!(xml, " w:leader=\"{}\"", escape_xml(leader))
+                            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    }
+                    xml.push_str("/>");
+                }
+                xml.push_str("</w:tabs>");
+            }
+
             // Write spacing
             if self.properties.space_before.is_some()
                 || self.properties.space_after.is_some()
@@ -521,22 +586,10 @@ impl MutableParagraph {
                 xml.push_str("/>");
             }
 
-            // Write tab stops
-            if !self.properties.tab_stops.is_empty() {
-                xml.push_str("<w:tabs>");
-                for tab_stop in &self.properties.tab_stops {
-                    xml.push_str("<w:tab");
-                    write!(xml, " w:val=\"{}\"", escape_xml(&tab_stop.alignment))
-                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                    write!(xml, " w:pos=\"{}\"", tab_stop.position)
-                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                    if let Some(ref leader) = tab_stop.leader {
-                        write!(xml, " w:leader=\"{}\"", escape_xml(leader))
-                            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                    }
-                    xml.push_str("/>");
-                }
-                xml.push_str("</w:tabs>");
+            // Write alignment (last of the supported properties in schema order)
+            if let Some(alignment) = self.properties.alignment {
+                write!(xml, "<w:jc w:val=\"{}\"/>", alignment.as_str())
+                    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
             }
 
             xml.push_str("</w:pPr>");
@@ -3,6 +3,7 @@
 /// This module handles the mapping between document elements (hyperlinks, images)
 /// and their relationship IDs in the OOXML package. Unlike the placeholder approach,
 /// this creates relationships first and provides the actual IDs for XML generation.
+use crate::ooxml::docx::enums::WdHeaderFooter;
 use std::collections::HashMap;
 
 /// Maps document elements to their relationship IDs.
@@ -16,10 +17,10 @@ pub struct RelationshipMapper {
     hyperlink_ids: HashMap<usize, String>,
     /// Maps image index to relationship ID
     image_ids: HashMap<usize, String>,
-    /// Header relationship ID (if any)
-    header_id: Option<String>,
-    /// Footer relationship ID (if any)
-    footer_id: Option<String>,
+    /// Header relationship IDs by kind
+    header_ids: Vec<(WdHeaderFooter, String)>,
+    /// Footer relationship IDs by kind
+    footer_ids: Vec<(WdHeaderFooter, String)>,
     /// Footnotes relationship ID (if any)
     footnotes_id: Option<String>,
     /// Endnotes relationship ID (if any)
@@ -42,14 +43,26 @@ impl RelationshipMapper {
         self.image_ids.insert(index, rel_id);
     }
 
-    /// Set the header relationship ID.
-    pub fn set_header_id(&mut self, rel_id: String) {
-        self.header_id = Some(rel_id);
+    /// Set the relationship ID of the header of the given kind.
+    pub fn set_header_reference(&mut self, kind: WdHeaderFooter, rel_id: String) {
+        self.header_ids.retain(|(k, _)| *k != kind);
+        self.header_ids.push((kind, rel_id));
     }
 
-    /// Set the footer relationship ID.
-    pub fn set_footer_id(&mut self, rel_id: String) {
-        self.footer_id = Some(rel_id);
+    /// Set the relationship ID of the footer of the given kind.
+    pub fn set_footer_reference(&mut self, kind: WdHeaderFooter, rel_id: String) {
+        self.footer_ids.retain(|(k, _)| *k != kind);
+        self.footer_ids.push((kind, rel_id));
+    }
+
+    /// Get the header relationship IDs by kind, in the order they were set.
+    pub fn header_references(&self) -> &[(WdHeaderFooter, String)] {
+        &self.header_ids
+    }
+
+    /// Get the footer relationship IDs by kind, in the order they were set.
+    pub fn footer_references(&self) -> &[(WdHeaderFooter, String)] {
+        &self.footer_ids
     }
 
     /// Get the relationship ID for a hyperlink by index.
@@ -62,16 +75,6 @@ impl RelationshipMapper {
         self.image_ids.get(&index).map(|s| s.as_str())
     }
 
-    /// Get the header relationship ID.
-    pub fn get_header_id(&self) -> Option<&str> {
-        self.header_id.as_deref()
-    }
-
-    /// Get the footer relationship ID.
-    pub fn get_footer_id(&self) -> Option<&str> {
-        self.footer_id.as_deref()
-    }
-
     /// Set the footnotes relationship ID.
     pub fn set_footnotes_id(&mut self, rel_id: String) {
         self.footnotes_id = Some(rel_id);
@@ -102,8 +105,8 @@ mod tests {
         let mapper = RelationshipMapper::new();
         assert!(mapper.hyperlink_ids.is_empty());
         assert!(mapper.image_ids.is_empty());
-        assert!(mapper.header_id.is_none());
-        assert!(mapper.footer_id.is_none());
+        assert!(mapper.header_ids.is_empty());
+        assert!(mapper.footer_ids.is_empty());
         assert!(mapper.footnotes_id.is_none());
         assert!(mapper.endnotes_id.is_none());
     }
@@ -138,21 +141,32 @@ mod tests {
     }
 
     #[test]
-    fn test_set_and_get_header_id() {
+    fn test_header_references_by_kind() {
         let mut mapper = RelationshipMapper::new();
-        assert_eq!(mapper.get_header_id(), None);
+        mapper.set_header_reference(WdHeaderFooter::FirstPage, "rId7".to_string());
+        mapper.set_header_reference(WdHeaderFooter::Primary, "rId8".to_string());
+        mapper.set_header_reference(WdHeaderFooter::FirstPage, "rId9".to_string());
 
-        mapper.set_header_id("rId10".to_string());
-        assert_eq!(mapper.get_header_id(), Some("rId10"));
+        assert_eq!(
+            mapper.header_references(),
+            [
+                (WdHeaderFooter::Primary, "rId8".to_string()),
+                (WdHeaderFooter::FirstPage, "rId9".to_string())
+            ]
+        );
+        assert!(mapper.footer_references().is_empty());
     }
 
     #[test]
-    fn test_set_and_get_footer_id() {
+    fn test_footer_references_by_kind() {
         let mut mapper = RelationshipMapper::new();
-        assert_eq!(mapper.get_footer_id(), None);
+        mapper.set_footer_reference(WdHeaderFooter::EvenPage, "rId11".to_string());
 
-        mapper.set_footer_id("rId11".to_string());
-        assert_eq!(mapper.get_footer_id(), Some("rId11"));
+        assert_eq!(
+            mapper.footer_references(),
+            [(WdHeaderFooter::EvenPage, "rId11".to_string())]
+        );
+        assert!(mapper.header_references().is_empty());
     }
 
     #[test]
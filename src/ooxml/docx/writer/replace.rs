@@ -0,0 +1,490 @@
+//! Find and replace for mutable DOCX documents.
+//!
+//! Word splits text into runs wherever formatting, spell-checking or editing
+//! history changes, so a phrase such as `{{customer}}` is frequently stored as
+//! several runs (`{{cust` + `omer}}`). Replacement therefore works on the
+//! joined text of consecutive text runs and then maps every match back onto
+//! the runs it covers:
+//!
+//! - the replacement takes the formatting of the first run of the match;
+//! - the remaining runs of the match are shrunk to their unmatched suffix, or
+//!   removed when nothing is left of them;
+//! - bookmark markers inside a match are moved to the edges of the replacement,
+//!   starts before it and ends after it, so the bookmark still covers it.
+//!
+//! Field instruction text (such as `REF Customer \h`) is skipped unless
+//! [`ReplaceOptions::include_field_instructions`] is set.
+//!
+//! # Examples
+//!
+//! ```rust
+//! use litchi::ooxml::docx::writer::MutableDocument;
+//! use std::collections::HashMap;
+//!
+//! let mut doc = MutableDocument::new();
+//! let para = doc.add_paragraph();
+//! para.add_run_with_text("Dear {{cust");
+//! para.add_run_with_text("omer}},").bold(true);
+//!
+//! let values = HashMap::from([("customer", "Ada")]);
+//! assert_eq!(doc.replace_placeholders(&values), 1);
+//! assert_eq!(doc.replace_text("Dear", "Hello"), 1);
+//! ```
+
+use super::field::MutableField;
+use super::hyperlink::HyperlinkElement;
+use super::paragraph::ParagraphElement;
+use super::run::{MutableRun, RunContent};
+use std::ops::Range;
+
+/// Options controlling how text is replaced.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplaceOptions {
+    /// Whether matches inside field instructions are replaced as well
+    ///
+    /// Instructions are the hidden codes of fields (`PAGE`, `REF Bookmark`,
+    /// `MERGEFIELD Name`); their displayed results are ordinary runs and are
+    /// always replaced.
+    pub include_field_instructions: bool,
+}
+
+impl ReplaceOptions {
+    /// Create options that only replace displayed text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set whether matches inside field instructions are replaced as well.
+    pub fn with_field_instructions(mut self, include: bool) -> Self {
+        self.include_field_instructions = include;
+        self
+    }
+}
+
+/// A single replacement found in a piece of text: a byte range and its new text.
+pub(crate) type Edit = (Range<usize>, String);
+
+/// Finds the non-overlapping edits to apply to a piece of text, in ascending order.
+pub(crate) type Finder<'a> = dyn Fn(&str) -> Vec<Edit> + 'a;
+
+/// Find every occurrence of `old` in `text`.
+pub(crate) fn literal_edits(text: &str, old: &str, new: &str) -> Vec<Edit> {
+    text.match_indices(old)
+        .map(|(start, matched)| (start..start + matched.len(), new.to_string()))
+        .collect()
+}
+
+/// Find every `{{key}}` in `text` whose trimmed key `lookup` knows about.
+///
+/// Unknown keys are left untouched, so several maps can be applied in turn.
+pub(crate) fn placeholder_edits<'v>(
+    text: &str,
+    lookup: impl Fn(&str) -> Option<&'v str>,
+) -> Vec<Edit> {
+    let mut edits = Vec::new();
+    let mut pos = 0;
+    while let Some(open) = text[pos..].find("{{").map(|i| pos + i) {
+        let Some(close) = text[open + 2..].find("}}").map(|i| open + 2 + i) else {
+            break;
+        };
+        match lookup(text[open + 2..close].trim()) {
+            Some(value) => {
+                edits.push((open..close + 2, value.to_string()));
+                pos = close + 2;
+            },
+            None => pos = open + 2,
+        }
+    }
+    edits
+}
+
+/// How an element takes part in the joined text of a run sequence.
+enum Role<'a> {
+    /// A run of text, optionally followed by a line break
+    Text(&'a str, bool),
+    /// A bookmark start or end marker, which has no text of its own
+    Marker { start: bool },
+    /// Anything else; matches never extend across it
+    Barrier,
+}
+
+/// An element sequence (paragraph or hyperlink content) that can be searched.
+trait RunSequence: Sized {
+    fn role(&self) -> Role<'_>;
+    fn run_mut(&mut self) -> Option<&mut MutableRun>;
+    fn field_mut(&mut self) -> Option<&mut MutableField>;
+    fn from_run(run: MutableRun) -> Self;
+}
+
+impl RunSequence for ParagraphElement {
+    fn role(&self) -> Role<'_> {
+        match self {
+            ParagraphElement::Run(run) => run_role(run),
+            ParagraphElement::BookmarkStart(_) => Role::Marker { start: true },
+            ParagraphElement::BookmarkEnd(_) => Role::Marker { start: false },
+            _ => Role::Barrier,
+        }
+    }
+
+    fn run_mut(&mut self) -> Option<&mut MutableRun> {
+        match self {
+            ParagraphElement::Run(run) => Some(run),
+            _ => None,
+        }
+    }
+
+    fn field_mut(&mut self) -> Option<&mut MutableField> {
+        match self {
+            ParagraphElement::Field(field) => Some(field),
+            _ => None,
+        }
+    }
+
+    fn from_run(run: MutableRun) -> Self {
+        ParagraphElement::Run(run)
+    }
+}
+
+impl RunSequence for HyperlinkElement {
+    fn role(&self) -> Role<'_> {
+        match self {
+            HyperlinkElement::Run(run) => run_role(run),
+            HyperlinkElement::Field(_) => Role::Barrier,
+        }
+    }
+
+    fn run_mut(&mut self) -> Option<&mut MutableRun> {
+        match self {
+            HyperlinkElement::Run(run) => Some(run),
+            HyperlinkElement::Field(_) => None,
+        }
+    }
+
+    fn field_mut(&mut self) -> Option<&mut MutableField> {
+        match self {
+            HyperlinkElement::Field(field) => Some(field),
+            HyperlinkElement::Run(_) => None,
+        }
+    }
+
+    fn from_run(run: MutableRun) -> Self {
+        HyperlinkElement::Run(run)
+    }
+}
+
+fn run_role(run: &MutableRun) -> Role<'_> {
+    match &run.content {
+        RunContent::Text(text) => Role::Text(text, run.properties.has_break),
+        _ => Role::Barrier,
+    }
+}
+
+fn run_text_mut(run: &mut MutableRun) -> &mut String {
+    match &mut run.content {
+        RunContent::Text(text) => text,
+        _ => unreachable!("only text runs take part in replacement"),
+    }
+}
+
+/// Apply `edits` (ascending, non-overlapping) to a plain string.
+fn apply_to_string(text: &mut String, edits: Vec<Edit>) -> usize {
+    let count = edits.len();
+    for (range, new) in edits.into_iter().rev() {
+        text.replace_range(range, &new);
+    }
+    count
+}
+
+/// Replace inside the instruction text of a field.
+pub(crate) fn replace_in_field(field: &mut MutableField, find: &Finder<'_>) -> usize {
+    match field {
+        MutableField::Complete { instruction, .. } | MutableField::Instruction(instruction) => {
+            let edits = find(instruction);
+            apply_to_string(instruction, edits)
+        },
+        _ => 0,
+    }
+}
+
+/// Replace within a paragraph's elements, including its hyperlinks.
+pub(crate) fn replace_in_paragraph(
+    elements: &mut Vec<ParagraphElement>,
+    find: &Finder<'_>,
+    options: &ReplaceOptions,
+) -> usize {
+    let mut count = 0;
+    for element in elements.iter_mut() {
+        if let ParagraphElement::Hyperlink(link) = element {
+            if link.elements.is_empty() {
+                if let Some(text) = link.text.as_mut() {
+                    let edits = find(text);
+                    count += apply_to_string(text, edits);
+                }
+            } else {
+                count += replace_in_sequence(&mut link.elements, find, options);
+            }
+        }
+    }
+    count + replace_in_sequence(elements, find, options)
+}
+
+/// Replace within one element sequence.
+fn replace_in_sequence<E: RunSequence>(
+    elements: &mut Vec<E>,
+    find: &Finder<'_>,
+    options: &ReplaceOptions,
+) -> usize {
+    let mut count = 0;
+    if options.include_field_instructions {
+        for element in elements.iter_mut() {
+            if let Some(field) = element.field_mut() {
+                count += replace_in_field(field, find);
+            }
+        }
+    }
+
+    // Group consecutive text runs; each group is searched as one string.
+    // A group holds (element index, byte offset of the run in the joined text).
+    let mut groups: Vec<(String, Vec<(usize, usize)>)> = Vec::new();
+    let mut open = false;
+    for (index, element) in elements.iter().enumerate() {
+        match element.role() {
+            Role::Text(text, has_break) => {
+                if !open {
+                    groups.push((String::new(), Vec::new()));
+                }
+                let (joined, runs) = groups.last_mut().unwrap();
+                runs.push((index, joined.len()));
+                joined.push_str(text);
+                open = !has_break;
+            },
+            Role::Marker { .. } => {},
+            Role::Barrier => open = false,
+        }
+    }
+
+    // Work backwards so that edits never shift the positions still to be used.
+    for (joined, runs) in groups.into_iter().rev() {
+        let edits = find(&joined);
+        count += edits.len();
+        for (range, new) in edits.into_iter().rev() {
+            apply_edit(elements, &runs, range, &new);
+        }
+    }
+    count
+}
+
+/// Apply one edit, given in joined-text bytes, to the runs of a group.
+fn apply_edit<E: RunSequence>(
+    elements: &mut Vec<E>,
+    runs: &[(usize, usize)],
+    range: Range<usize>,
+    new: &str,
+) {
+    // The run holding a byte is the last one starting at or before it.
+    let locate = |byte: usize| runs.partition_point(|&(_, offset)| offset <= byte) - 1;
+    let (first, first_offset) = runs[locate(range.start)];
+    let (last, last_offset) = runs[locate(range.end - 1)];
+
+    if first == last {
+        let run = elements[first].run_mut().unwrap();
+        run_text_mut(run).replace_range(range.start - first_offset..range.end - first_offset, new);
+        return;
+    }
+
+    // Keep only the unmatched tail of the last run.
+    let last_run = elements[last].run_mut().unwrap();
+    let tail = run_text_mut(last_run);
+    tail.replace_range(..range.end - last_offset, "");
+    if tail.is_empty() && !last_run.properties.has_break {
+        elements.remove(last);
+    }
+
+    // Drop the runs in between, keeping any bookmark markers they enclosed.
+    let mut starts = Vec::new();
+    let mut ends = Vec::new();
+    for element in elements.drain(first + 1..last) {
+        match element.role() {
+            Role::Marker { start: true } => starts.push(element),
+            Role::Marker { start: false } => ends.push(element),
+            _ => {},
+        }
+    }
+
+    // The first run keeps its text before the match followed by the
+    // replacement. Moved bookmark starts must sit between the two, so the
+    // replacement then gets a run of its own with the same formatting.
+    let prefix_len = range.start - first_offset;
+    let first_run = elements[first].run_mut().unwrap();
+    let text = run_text_mut(first_run);
+    if starts.is_empty() {
+        text.truncate(prefix_len);
+        text.push_str(new);
+        if text.is_empty() {
+            elements.remove(first);
+            elements.splice(first..first, ends);
+        } else {
+            elements.splice(first + 1..first + 1, ends);
+        }
+        return;
+    }
+    let (replacement, at) = if prefix_len == 0 {
+        text.replace_range(.., new);
+        (elements.remove(first), first)
+    } else {
+        text.truncate(prefix_len);
+        let run = MutableRun {
+            content: RunContent::Text(new.to_string()),
+            properties: first_run.properties.clone(),
+        };
+        (E::from_run(run), first + 1)
+    };
+    let mut inserted = starts;
+    if !new.is_empty() {
+        inserted.push(replacement);
+    }
+    inserted.extend(ends);
+    elements.splice(at..at, inserted);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::docx::writer::{MutableDocument, MutableParagraph};
+    use std::collections::HashMap;
+
+    /// Describe a paragraph as run texts (bold runs prefixed with `*`) and bookmark markers.
+    fn describe(para: &MutableParagraph) -> Vec<String> {
+        para.elements
+            .iter()
+            .map(|element| match element {
+                ParagraphElement::Run(run) => {
+                    let bold = if run.properties.bold == Some(true) {
+                        "*"
+                    } else {
+                        ""
+                    };
+                    format!("{}{}", bold, run.get_text())
+                },
+                ParagraphElement::BookmarkStart(b) => format!("[{}", b.name()),
+                ParagraphElement::BookmarkEnd(id) => format!("{}]", id),
+                _ => "?".to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replace_within_run() {
+        let mut para = MutableParagraph::new();
+        para.add_run_with_text("one two one").bold(true);
+        assert_eq!(para.replace_text("one", "three"), 2);
+        assert_eq!(describe(&para), ["*three two three"]);
+    }
+
+    #[test]
+    fn test_replace_across_runs_keeps_first_format() {
+        let mut para = MutableParagraph::new();
+        para.add_run_with_text("Hel").bold(true);
+        para.add_run_with_text("lo Wo");
+        para.add_run_with_text("rld!");
+        assert_eq!(para.replace_text("Hello World", "Hi"), 1);
+        assert_eq!(describe(&para), ["*Hi", "!"]);
+    }
+
+    #[test]
+    fn test_replace_from_middle_of_run() {
+        let mut para = MutableParagraph::new();
+        para.add_run_with_text("Say Hel").bold(true);
+        para.add_run_with_text("lo").italic(true);
+        assert_eq!(para.replace_text("Hello", "Bye"), 1);
+        assert_eq!(describe(&para), ["*Say Bye"]);
+
+        // A bookmark starting inside the match needs its own replacement run.
+        let mut para = MutableParagraph::new();
+        para.add_run_with_text("Say Hel").bold(true);
+        para.add_bookmark_start(3, "greeting");
+        para.add_run_with_text("lo");
+        para.add_bookmark_end(3);
+        assert_eq!(para.replace_text("Hello", "Bye"), 1);
+        assert_eq!(describe(&para), ["*Say ", "[greeting", "*Bye", "3]"]);
+    }
+
+    #[test]
+    fn test_replace_widens_bookmarks() {
+        let mut para = MutableParagraph::new();
+        para.add_run_with_text("Hel");
+        para.add_bookmark_start(1, "mark");
+        para.add_run_with_text("lo");
+        para.add_bookmark_end(1);
+        para.add_run_with_text(" world");
+        assert_eq!(para.replace_text("Hello w", "Goodbye W"), 1);
+        assert_eq!(describe(&para), ["[mark", "Goodbye W", "1]", "orld"]);
+    }
+
+    #[test]
+    fn test_replace_with_empty_text_removes_runs() {
+        let mut para = MutableParagraph::new();
+        para.add_run_with_text("a");
+        para.add_run_with_text("bc");
+        para.add_run_with_text("d");
+        assert_eq!(para.replace_text("abc", ""), 1);
+        assert_eq!(describe(&para), ["d"]);
+    }
+
+    #[test]
+    fn test_replace_stops_at_line_break() {
+        let mut para = MutableParagraph::new();
+        para.add_run_with_text("foo").add_break();
+        para.add_run_with_text("bar");
+        assert_eq!(para.replace_text("foobar", "x"), 0);
+        assert_eq!(para.replace_text("foo", "x"), 1);
+        assert_eq!(para.elements.len(), 2);
+    }
+
+    #[test]
+    fn test_field_instructions_skipped_by_default() {
+        let mut doc = MutableDocument::new();
+        let para = doc.add_paragraph();
+        para.add_run_with_text("See Old");
+        para.add_field(MutableField::with_result(
+            "REF Old \\h".to_string(),
+            "Old".to_string(),
+        ));
+        assert_eq!(doc.replace_text("Old", "New"), 1);
+
+        let options = ReplaceOptions::new().with_field_instructions(true);
+        assert_eq!(doc.replace_text_with("Old", "New", &options), 1);
+        let para = doc.paragraph(0).unwrap();
+        match &para.elements[1] {
+            ParagraphElement::Field(MutableField::Complete { instruction, .. }) => {
+                assert_eq!(instruction, "REF New \\h");
+            },
+            other => panic!("unexpected element {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_replace_placeholders() {
+        let mut doc = MutableDocument::new();
+        let para = doc.add_paragraph();
+        para.add_run_with_text("Dear {{ na").bold(true);
+        para.add_run_with_text("me }}, your {{unknown}} order {{id}}");
+        let table = doc.add_table(1, 1);
+        table.cell(0, 0).unwrap().set_text("{{id}}");
+        doc.add_header_paragraph().add_run_with_text("{{name}}");
+
+        let values = HashMap::from([("name".to_string(), "Ada"), ("id".to_string(), "42")]);
+        assert_eq!(doc.replace_placeholders(&values), 4);
+        assert_eq!(
+            describe(doc.paragraph(0).unwrap()),
+            ["*Dear Ada", ", your {{unknown}} order 42"]
+        );
+        assert!(doc.is_modified());
+    }
+
+    #[test]
+    fn test_placeholder_edits() {
+        let edits = placeholder_edits("{{a}} {{b} {{ a }}}}", |key| (key == "a").then_some("x"));
+        assert_eq!(edits, [(0..5, "x".to_string()), (11..18, "x".to_string())]);
+    }
+}
@@ -88,6 +88,12 @@ impl MutableRun {
         }
     }
 
+    /// Set the character style (by style ID).
+    pub fn style(&mut self, style_id: &str) -> &mut Self {
+        self.properties.style = Some(style_id.to_string());
+        self
+    }
+
     /// Make the text bold.
     pub fn bold(&mut self, bold: bool) -> &mut Self {
         self.properties.bold = Some(bold);
@@ -179,16 +185,22 @@ impl MutableRun {
         if self.properties.has_properties() {
             xml.push_str("<w:rPr>");
 
-            if let Some(bold) = self.properties.bold
-                && bold
-            {
-                xml.push_str("<w:b/>");
+            if let Some(ref style) = self.properties.style {
+                write!(xml, "<w:rStyle w:val=\"{}\"/>", escape_xml(style))
+                    .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+            }
+
+            // An explicit false turns off formatting inherited from styles
+            match self.properties.bold {
+                Some(true) => xml.push_str("<w:b/>"),
+                Some(false) => xml.push_str("<w:b w:val=\"0\"/>"),
+                None => {},
             }
 
-            if let Some(italic) = self.properties.italic
-                && italic
-            {
-                xml.push_str("<w:i/>");
+            match self.properties.italic {
+                Some(true) => xml.push_str("<w:i/>"),
+                Some(false) => xml.push_str("<w:i w:val=\"0\"/>"),
+                None => {},
             }
 
             if let Some(underline_style) = self.properties.underline {
@@ -258,7 +270,7 @@ impl MutableRun {
                 write!(
                     xml,
                     "<w:instrText xml:space=\"preserve\">PAGE \\* {}</w:instrText></w:r><w:r>",
-                    format.as_str()
+                    format.field_switch()
                 )
                 .map_err(|e| OoxmlError::Xml(e.to_string()))?;
                 // Field separate
@@ -333,8 +345,9 @@ impl MutableRun {
 }
 
 /// Run properties.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub(crate) struct RunProperties {
+    pub(crate) style: Option<String>,
     pub(crate) bold: Option<bool>,
     pub(crate) italic: Option<bool>,
     pub(crate) underline: Option<UnderlineStyle>,
@@ -349,7 +362,8 @@ pub(crate) struct RunProperties {
 
 impl RunProperties {
     pub(crate) fn has_properties(&self) -> bool {
-        self.bold.is_some()
+        self.style.is_some()
+            || self.bold.is_some()
             || self.italic.is_some()
             || self.underline.is_some()
             || self.font_size.is_some()
@@ -1,5 +1,7 @@
+use crate::common::Length;
+
 /// Page number format for headers/footers.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PageNumberFormat {
     /// Decimal numbers (1, 2, 3, ...)
     Decimal,
@@ -14,6 +16,7 @@ pub enum PageNumberFormat {
 }
 
 impl PageNumberFormat {
+    /// Convert format to the `w:fmt` XML string representation.
     pub(crate) fn as_str(&self) -> &'static str {
         match self {
             Self::Decimal => "decimal",
@@ -23,6 +26,17 @@ impl PageNumberFormat {
             Self::LowerLetter => "lowerLetter",
         }
     }
+
+    /// Field format switch (`\* ...`) for `PAGE` fields.
+    pub(crate) fn field_switch(&self) -> &'static str {
+        match self {
+            Self::Decimal => "Arabic",
+            Self::UpperRoman => "ROMAN",
+            Self::LowerRoman => "roman",
+            Self::UpperLetter => "ALPHABETIC",
+            Self::LowerLetter => "alphabetic",
+        }
+    }
 }
 
 /// Page orientation.
@@ -64,6 +78,14 @@ pub struct SectionProperties {
     pub header_distance: u32,
     /// Footer distance from bottom in twips
     pub footer_distance: u32,
+    /// Number of text columns
+    pub columns: u32,
+    /// Space between text columns in twips
+    pub column_spacing: u32,
+    /// Page number the section restarts numbering at
+    pub page_number_start: Option<u32>,
+    /// Page number format of the section
+    pub page_number_format: Option<PageNumberFormat>,
 }
 
 impl Default for SectionProperties {
@@ -79,6 +101,10 @@ impl Default for SectionProperties {
             margin_right: 1440,   // 1 inch
             header_distance: 720, // 0.5 inch
             footer_distance: 720, // 0.5 inch
+            columns: 1,
+            column_spacing: 720, // 0.5 inch
+            page_number_start: None,
+            page_number_format: None,
         }
     }
 }
@@ -117,10 +143,30 @@ impl SectionProperties {
 
     /// Set margins (all in inches).
     pub fn margins(mut self, top: f64, bottom: f64, left: f64, right: f64) -> Self {
-        self.margin_top = (top * 1440.0) as u32;
-        self.margin_bottom = (bottom * 1440.0) as u32;
-        self.margin_left = (left * 1440.0) as u32;
-        self.margin_right = (right * 1440.0) as u32;
+        let twips = |inches: f64| Length::from_inches(inches).twips().max(0) as u32;
+        self.margin_top = twips(top);
+        self.margin_bottom = twips(bottom);
+        self.margin_left = twips(left);
+        self.margin_right = twips(right);
+        self
+    }
+
+    /// Set the number of text columns and the space between them (in inches).
+    pub fn columns(mut self, count: u32, spacing: f64) -> Self {
+        self.columns = count.max(1);
+        self.column_spacing = Length::from_inches(spacing).twips().max(0) as u32;
+        self
+    }
+
+    /// Restart page numbering at `start` in this section.
+    pub fn restart_page_numbering(mut self, start: u32) -> Self {
+        self.page_number_start = Some(start);
+        self
+    }
+
+    /// Set the page number format of this section.
+    pub fn page_number_format(mut self, format: PageNumberFormat) -> Self {
+        self.page_number_format = Some(format);
         self
     }
 }
@@ -136,6 +182,8 @@ mod tests {
         assert_eq!(PageNumberFormat::LowerRoman.as_str(), "lowerRoman");
         assert_eq!(PageNumberFormat::UpperLetter.as_str(), "upperLetter");
         assert_eq!(PageNumberFormat::LowerLetter.as_str(), "lowerLetter");
+        assert_eq!(PageNumberFormat::Decimal.field_switch(), "Arabic");
+        assert_eq!(PageNumberFormat::LowerRoman.field_switch(), "roman");
     }
 
     #[test]
@@ -217,6 +265,21 @@ mod tests {
         assert_eq!(props.margin_left, 2160); // 1.5 * 1440
     }
 
+    #[test]
+    fn test_section_properties_columns_and_numbering() {
+        let props = SectionProperties::default()
+            .columns(2, 0.25)
+            .restart_page_numbering(1)
+            .page_number_format(PageNumberFormat::LowerRoman);
+        assert_eq!(props.columns, 2);
+        assert_eq!(props.column_spacing, 360); // 0.25 * 1440
+        assert_eq!(props.page_number_start, Some(1));
+        assert_eq!(props.page_number_format, Some(PageNumberFormat::LowerRoman));
+
+        // At least one column
+        assert_eq!(SectionProperties::default().columns(0, 0.5).columns, 1);
+    }
+
     #[test]
     fn test_page_number_format_debug() {
         let format = PageNumberFormat::UpperRoman;
@@ -1,9 +1,15 @@
 //! Style writer support for DOCX documents.
 //!
 //! This module provides functionality for creating and writing document styles.
+use super::fragment::{related_part, splice};
 use crate::common::xml::escape_xml;
 use crate::ooxml::docx::enums::WdStyleType;
+use crate::ooxml::docx::fragment::parse_tree;
 use crate::ooxml::error::Result;
+use crate::ooxml::opc::OpcPackage;
+use crate::ooxml::opc::constants::{content_type as ct, relationship_type as rt};
+use crate::ooxml::opc::packuri::PackURI;
+use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
 
 /// A mutable style definition for writing.
@@ -21,6 +27,7 @@ use std::fmt::Write as FmtWrite;
 /// // Create a custom paragraph style
 /// let mut style = MutableStyle::new("MyStyle", "My Custom Style", WdStyleType::Paragraph);
 /// style.set_based_on(Some("Normal".to_string()));
+/// style.set_next(Some("Normal".to_string()));
 /// style.set_font_size(Some(24)); // 12pt (half-points)
 /// style.set_bold(true);
 /// ```
@@ -38,6 +45,10 @@ pub struct MutableStyle {
     is_custom: bool,
     /// ID of the style this is based on
     based_on: Option<String>,
+    /// ID of the style of the paragraph following this one
+    next: Option<String>,
+    /// ID of the linked paragraph or character style
+    link: Option<String>,
     /// UI priority for display ordering (lower = higher priority)
     priority: Option<i32>,
     /// Whether to show in quick style gallery
@@ -50,14 +61,22 @@ pub struct MutableStyle {
     font_name: Option<String>,
     /// Font size in half-points (e.g., 24 = 12pt)
     font_size: Option<u32>,
-    /// Bold formatting
-    bold: bool,
-    /// Italic formatting
-    italic: bool,
+    /// Bold formatting (`Some(false)` turns off inherited bold)
+    bold: Option<bool>,
+    /// Italic formatting (`Some(false)` turns off inherited italic)
+    italic: Option<bool>,
     /// Underline formatting
     underline: bool,
+    /// Strikethrough formatting
+    strike: bool,
+    /// Small capitals
+    small_caps: bool,
+    /// All capitals
+    all_caps: bool,
     /// Font color (RGB hex format, e.g., "FF0000" for red)
     color: Option<String>,
+    /// Highlight color (e.g., "yellow")
+    highlight: Option<String>,
     /// Paragraph alignment for paragraph styles
     alignment: Option<String>,
     /// Space before paragraph in twips (1/1440 inch)
@@ -72,6 +91,14 @@ pub struct MutableStyle {
     indent_right: Option<i32>,
     /// First line indent in twips (negative for hanging)
     indent_first_line: Option<i32>,
+    /// Keep the paragraph on the same page as the next one
+    keep_with_next: bool,
+    /// Keep the lines of the paragraph on the same page
+    keep_lines_together: bool,
+    /// Start the paragraph on a new page
+    page_break_before: bool,
+    /// Outline level (0-based, 0 = "Heading 1" level)
+    outline_level: Option<u8>,
 }
 
 impl MutableStyle {
@@ -104,16 +131,22 @@ impl MutableStyle {
             is_default: false,
             is_custom: true,
             based_on: None,
+            next: None,
+            link: None,
             priority: None,
             is_quick_style: false,
             is_hidden: false,
             is_locked: false,
             font_name: None,
             font_size: None,
-            bold: false,
-            italic: false,
+            bold: None,
+            italic: None,
             underline: false,
+            strike: false,
+            small_caps: false,
+            all_caps: false,
             color: None,
+            highlight: None,
             alignment: None,
             space_before: None,
             space_after: None,
@@ -121,6 +154,10 @@ impl MutableStyle {
             indent_left: None,
             indent_right: None,
             indent_first_line: None,
+            keep_with_next: false,
+            keep_lines_together: false,
+            page_break_before: false,
+            outline_level: None,
         }
     }
 
@@ -182,6 +219,32 @@ impl MutableStyle {
         self.based_on.as_deref()
     }
 
+    /// Set the ID of the style Word applies to the paragraph that follows
+    /// one with this style.
+    pub fn set_next(&mut self, next: Option<String>) {
+        self.next = next;
+    }
+
+    /// Get the ID of the style of the following paragraph.
+    #[inline]
+    pub fn next(&self) -> Option<&str> {
+        self.next.as_deref()
+    }
+
+    /// Set the ID of the linked style.
+    ///
+    /// A paragraph style can be linked to a character style with the same
+    /// run formatting, so that it can also be applied to part of a paragraph.
+    pub fn set_link(&mut self, link: Option<String>) {
+        self.link = link;
+    }
+
+    /// Get the ID of the linked style.
+    #[inline]
+    pub fn link(&self) -> Option<&str> {
+        self.link.as_deref()
+    }
+
     /// Set the UI priority (lower values appear first).
     pub fn set_priority(&mut self, priority: Option<i32>) {
         self.priority = priority;
@@ -213,13 +276,18 @@ impl MutableStyle {
     }
 
     /// Set bold formatting.
+    ///
+    /// `false` is written explicitly, turning off bold inherited from the
+    /// base style.
     pub fn set_bold(&mut self, bold: bool) {
-        self.bold = bold;
+        self.bold = Some(bold);
     }
 
     /// Set italic formatting.
+    ///
+    /// `false` is written explicitly, like [`set_bold`](Self::set_bold).
     pub fn set_italic(&mut self, italic: bool) {
-        self.italic = italic;
+        self.italic = Some(italic);
     }
 
     /// Set underline formatting.
@@ -227,11 +295,31 @@ impl MutableStyle {
         self.underline = underline;
     }
 
+    /// Set strikethrough formatting.
+    pub fn set_strike(&mut self, strike: bool) {
+        self.strike = strike;
+    }
+
+    /// Set small capitals.
+    pub fn set_small_caps(&mut self, small_caps: bool) {
+        self.small_caps = small_caps;
+    }
+
+    /// Set all capitals.
+    pub fn set_all_caps(&mut self, all_caps: bool) {
+        self.all_caps = all_caps;
+    }
+
     /// Set the font color (RGB hex format, e.g., "FF0000" for red).
     pub fn set_color(&mut self, color: Option<String>) {
         self.color = color;
     }
 
+    /// Set the highlight color (e.g., "yellow").
+    pub fn set_highlight(&mut self, highlight: Option<String>) {
+        self.highlight = highlight;
+    }
+
     /// Set paragraph alignment ("left", "center", "right", "justify").
     pub fn set_alignment(&mut self, alignment: Option<String>) {
         self.alignment = alignment;
@@ -267,6 +355,26 @@ impl MutableStyle {
         self.indent_first_line = indent_first_line;
     }
 
+    /// Keep paragraphs with this style on the same page as the next one.
+    pub fn set_keep_with_next(&mut self, keep_with_next: bool) {
+        self.keep_with_next = keep_with_next;
+    }
+
+    /// Keep the lines of paragraphs with this style on the same page.
+    pub fn set_keep_lines_together(&mut self, keep_lines_together: bool) {
+        self.keep_lines_together = keep_lines_together;
+    }
+
+    /// Start paragraphs with this style on a new page.
+    pub fn set_page_break_before(&mut self, page_break_before: bool) {
+        self.page_break_before = page_break_before;
+    }
+
+    /// Set the outline level (0-based, 0 = "Heading 1" level).
+    pub fn set_outline_level(&mut self, outline_level: Option<u8>) {
+        self.outline_level = outline_level;
+    }
+
     /// Generate XML for this style.
     pub(crate) fn to_xml(&self) -> Result<String> {
         let mut xml = String::with_capacity(512);
@@ -296,41 +404,61 @@ impl MutableStyle {
             write!(&mut xml, r#"<w:basedOn w:val="{}"/>"#, escape_xml(based_on))?;
         }
 
+        // Next paragraph style
+        if let Some(ref next) = self.next {
+            write!(&mut xml, r#"<w:next w:val="{}"/>"#, escape_xml(next))?;
+        }
+
+        // Linked style
+        if let Some(ref link) = self.link {
+            write!(&mut xml, r#"<w:link w:val="{}"/>"#, escape_xml(link))?;
+        }
+
         // Priority
         if let Some(priority) = self.priority {
             write!(&mut xml, r#"<w:uiPriority w:val="{}"/>"#, priority)?;
         }
 
-        // Quick style
-        if self.is_quick_style {
-            xml.push_str("<w:qFormat/>");
-        }
-
         // Hidden
         if self.is_hidden {
             xml.push_str("<w:semiHidden/>");
         }
 
+        // Quick style
+        if self.is_quick_style {
+            xml.push_str("<w:qFormat/>");
+        }
+
         // Locked
         if self.is_locked {
             xml.push_str("<w:locked/>");
         }
 
-        // Paragraph properties (for paragraph and table styles)
+        // Paragraph properties (for paragraph and table styles), in schema order
         if matches!(self.style_type, WdStyleType::Paragraph | WdStyleType::Table) {
-            let has_para_props = self.alignment.is_some()
+            let has_para_props = self.keep_with_next
+                || self.keep_lines_together
+                || self.page_break_before
+                || self.alignment.is_some()
                 || self.space_before.is_some()
                 || self.space_after.is_some()
                 || self.line_spacing.is_some()
                 || self.indent_left.is_some()
                 || self.indent_right.is_some()
-                || self.indent_first_line.is_some();
+                || self.indent_first_line.is_some()
+                || self.outline_level.is_some();
 
             if has_para_props {
                 xml.push_str("<w:pPr>");
 
-                if let Some(ref alignment) = self.alignment {
-                    write!(&mut xml, r#"<w:jc w:val="{}"/>"#, alignment)?;
+                if self.keep_with_next {
+                    xml.push_str("<w:keepNext/>");
+                }
+                if self.keep_lines_together {
+                    xml.push_str("<w:keepLines/>");
+                }
+                if self.page_break_before {
+                    xml.push_str("<w:pageBreakBefore/>");
                 }
 
                 if self.space_before.is_some()
@@ -371,17 +499,29 @@ impl MutableStyle {
                     xml.push_str("/>");
                 }
 
+                if let Some(ref alignment) = self.alignment {
+                    write!(&mut xml, r#"<w:jc w:val="{}"/>"#, escape_xml(alignment))?;
+                }
+
+                if let Some(level) = self.outline_level {
+                    write!(&mut xml, r#"<w:outlineLvl w:val="{}"/>"#, level)?;
+                }
+
                 xml.push_str("</w:pPr>");
             }
         }
 
-        // Run properties (character formatting)
+        // Run properties (character formatting), in schema order
         let has_run_props = self.font_name.is_some()
             || self.font_size.is_some()
-            || self.bold
-            || self.italic
+            || self.bold.is_some()
+            || self.italic.is_some()
             || self.underline
-            || self.color.is_some();
+            || self.strike
+            || self.small_caps
+            || self.all_caps
+            || self.color.is_some()
+            || self.highlight.is_some();
 
         if has_run_props {
             xml.push_str("<w:rPr>");
@@ -396,16 +536,32 @@ impl MutableStyle {
                 )?;
             }
 
-            if self.bold {
-                xml.push_str("<w:b/>");
+            match self.bold {
+                Some(true) => xml.push_str("<w:b/>"),
+                Some(false) => xml.push_str(r#"<w:b w:val="0"/>"#),
+                None => {},
             }
 
-            if self.italic {
-                xml.push_str("<w:i/>");
+            match self.italic {
+                Some(true) => xml.push_str("<w:i/>"),
+                Some(false) => xml.push_str(r#"<w:i w:val="0"/>"#),
+                None => {},
             }
 
-            if self.underline {
-                xml.push_str(r#"<w:u w:val="single"/>"#);
+            if self.all_caps {
+                xml.push_str("<w:caps/>");
+            }
+
+            if self.small_caps {
+                xml.push_str("<w:smallCaps/>");
+            }
+
+            if self.strike {
+                xml.push_str("<w:strike/>");
+            }
+
+            if let Some(ref color) = self.color {
+                write!(&mut xml, r#"<w:color w:val="{}"/>"#, escape_xml(color))?;
             }
 
             if let Some(size) = self.font_size {
@@ -413,8 +569,16 @@ impl MutableStyle {
                 write!(&mut xml, r#"<w:szCs w:val="{}"/>"#, size)?;
             }
 
-            if let Some(ref color) = self.color {
-                write!(&mut xml, r#"<w:color w:val="{}"/>"#, escape_xml(color))?;
+            if let Some(ref highlight) = self.highlight {
+                write!(
+                    &mut xml,
+                    r#"<w:highlight w:val="{}"/>"#,
+                    escape_xml(highlight)
+                )?;
+            }
+
+            if self.underline {
+                xml.push_str(r#"<w:u w:val="single"/>"#);
             }
 
             xml.push_str("</w:rPr>");
@@ -640,6 +804,47 @@ pub fn generate_styles_xml(styles: &[MutableStyle]) -> Result<String> {
     Ok(xml)
 }
 
+/// Add styles to the styles part of the document, replacing the existing
+/// definitions with the same style IDs.
+pub(crate) fn apply_styles(
+    opc: &mut OpcPackage,
+    doc_uri: &PackURI,
+    styles: &[MutableStyle],
+) -> Result<()> {
+    if styles.is_empty() {
+        return Ok(());
+    }
+    let uri = related_part(
+        opc,
+        doc_uri,
+        rt::STYLES,
+        "/word/styles.xml",
+        ct::WML_STYLES,
+        "styles",
+    )?;
+    let xml = opc.get_part(&uri)?.try_blob()?.to_vec();
+    let root = parse_tree(&xml)?;
+    let existing: HashMap<String, _> = root
+        .children
+        .iter()
+        .filter(|node| node.local() == b"style")
+        .filter_map(|node| Some((node.attr(&xml, b"styleId")?, node.range.clone())))
+        .collect();
+
+    let closing = root.range.end - (root.name.len() + 3);
+    let mut edits = Vec::with_capacity(styles.len());
+    for style in styles {
+        let range = existing
+            .get(style.style_id())
+            .cloned()
+            .unwrap_or(closing..closing);
+        edits.push((range, style.to_xml()?.into_bytes()));
+    }
+    let xml = splice(&xml, edits);
+    opc.get_part_mut(&uri)?.set_blob(xml);
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
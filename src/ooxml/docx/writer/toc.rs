@@ -30,7 +30,7 @@ pub struct TableOfContents {
     page_numbers: bool,
     /// Right-align page numbers (default: true)
     right_align_page_numbers: bool,
-    /// Include outline levels (default: false)
+    /// Include paragraphs by their outline level (default: true)
     use_outline_levels: bool,
     /// Custom title (default: None, uses Word's default)
     title: Option<String>,
@@ -43,6 +43,7 @@ impl TableOfContents {
     /// - Heading levels 1-3
     /// - Includes hyperlinks
     /// - Includes page numbers (right-aligned)
+    /// - Includes paragraphs with outline levels
     ///
     /// # Examples
     ///
@@ -56,7 +57,7 @@ impl TableOfContents {
             hyperlinks: true,
             page_numbers: true,
             right_align_page_numbers: true,
-            use_outline_levels: false,
+            use_outline_levels: true,
             title: None,
         }
     }
@@ -97,7 +98,7 @@ impl TableOfContents {
         self
     }
 
-    /// Use outline levels instead of heading styles (default: false).
+    /// Also include paragraphs by their outline level (default: true).
     pub fn use_outline_levels(mut self, enabled: bool) -> Self {
         self.use_outline_levels = enabled;
         self
@@ -112,29 +113,21 @@ impl TableOfContents {
     /// Build the TOC field instruction string.
     ///
     /// Format: TOC \o "1-3" \h \z \u
-    /// - \o "1-3" = outline levels 1 through 3
+    /// - \o "1-3" = heading styles 1 through 3
     /// - \h = hyperlinks
     /// - \z = hide tab leader and page numbers in Web Layout view
-    /// - \u = use outline levels
+    /// - \u = use paragraph outline levels
+    /// - \n = omit page numbers (only when page numbers are disabled)
     pub fn build_field_instruction(&self) -> String {
         let mut instruction = String::from("TOC");
 
-        // Outline levels
-        if self.use_outline_levels {
-            write!(
-                &mut instruction,
-                r#" \u "{}-{}""#,
-                self.start_level, self.end_level
-            )
-            .unwrap();
-        } else {
-            write!(
-                &mut instruction,
-                r#" \o "{}-{}""#,
-                self.start_level, self.end_level
-            )
-            .unwrap();
-        }
+        // Heading levels
+        write!(
+            &mut instruction,
+            r#" \o "{}-{}""#,
+            self.start_level, self.end_level
+        )
+        .unwrap();
 
         // Hyperlinks
         if self.hyperlinks {
@@ -144,6 +137,16 @@ impl TableOfContents {
         // Hide in web layout
         instruction.push_str(" \\z");
 
+        // Outline levels
+        if self.use_outline_levels {
+            instruction.push_str(" \\u");
+        }
+
+        // Page numbers
+        if !self.page_numbers {
+            instruction.push_str(" \\n");
+        }
+
         instruction
     }
 
@@ -162,6 +165,21 @@ impl TableOfContents {
         self.end_level as u8
     }
 
+    /// Check if the entries link to their headings.
+    pub fn has_hyperlinks(&self) -> bool {
+        self.hyperlinks
+    }
+
+    /// Check if the entries show page numbers.
+    pub fn has_page_numbers(&self) -> bool {
+        self.page_numbers
+    }
+
+    /// Check if page numbers are right-aligned.
+    pub fn has_right_aligned_page_numbers(&self) -> bool {
+        self.right_align_page_numbers
+    }
+
     /// Generate XML for the TOC field.
     ///
     /// Returns a paragraph containing the TOC field with optional title.
@@ -289,6 +307,22 @@ mod tests {
         assert!(instr.contains(r#"\h"#));
     }
 
+    #[test]
+    fn test_toc_default_field_instruction() {
+        assert_eq!(
+            TableOfContents::new().build_field_instruction(),
+            r#"TOC \o "1-3" \h \z \u"#
+        );
+        assert_eq!(
+            TableOfContents::new()
+                .hyperlinks(false)
+                .use_outline_levels(false)
+                .page_numbers(false)
+                .build_field_instruction(),
+            r#"TOC \o "1-3" \z \n"#
+        );
+    }
+
     #[test]
     fn test_toc_xml() {
         let toc = TableOfContents::new().title("Contents");
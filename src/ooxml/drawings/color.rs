@@ -0,0 +1,681 @@
+//! DrawingML colors and theme color resolution.
+//!
+//! Most colors in real documents are references to a slot of the theme color
+//! scheme (`accent1`, `tx1`, ...) adjusted by transforms such as `lumMod` or
+//! `tint`. [`ColorSpec`] keeps the reference as written, and
+//! [`ColorSpec::resolve`] computes the displayed RGB value against a
+//! [`ColorScheme`].
+use crate::common::RGBColor;
+use crate::common::xml::attr;
+use crate::ooxml::error::{OoxmlError, Result};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+/// A slot of a theme color scheme (`a:clrScheme`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ThemeColorSlot {
+    /// Dark 1 (`dk1`), the default text color
+    Dark1,
+    /// Light 1 (`lt1`), the default background color
+    Light1,
+    /// Dark 2 (`dk2`)
+    Dark2,
+    /// Light 2 (`lt2`)
+    Light2,
+    /// Accent 1
+    Accent1,
+    /// Accent 2
+    Accent2,
+    /// Accent 3
+    Accent3,
+    /// Accent 4
+    Accent4,
+    /// Accent 5
+    Accent5,
+    /// Accent 6
+    Accent6,
+    /// Hyperlink (`hlink`)
+    Hyperlink,
+    /// Followed hyperlink (`folHlink`)
+    FollowedHyperlink,
+}
+
+impl ThemeColorSlot {
+    /// All slots, in color scheme order.
+    pub const ALL: [Self; 12] = [
+        Self::Dark1,
+        Self::Light1,
+        Self::Dark2,
+        Self::Light2,
+        Self::Accent1,
+        Self::Accent2,
+        Self::Accent3,
+        Self::Accent4,
+        Self::Accent5,
+        Self::Accent6,
+        Self::Hyperlink,
+        Self::FollowedHyperlink,
+    ];
+
+    /// Get the element name of the slot in `a:clrScheme`.
+    pub fn scheme_name(&self) -> &'static str {
+        match self {
+            Self::Dark1 => "dk1",
+            Self::Light1 => "lt1",
+            Self::Dark2 => "dk2",
+            Self::Light2 => "lt2",
+            Self::Accent1 => "accent1",
+            Self::Accent2 => "accent2",
+            Self::Accent3 => "accent3",
+            Self::Accent4 => "accent4",
+            Self::Accent5 => "accent5",
+            Self::Accent6 => "accent6",
+            Self::Hyperlink => "hlink",
+            Self::FollowedHyperlink => "folHlink",
+        }
+    }
+
+    /// Parse a DrawingML scheme color name (`a:schemeClr/@val`).
+    ///
+    /// The text and background aliases (`tx1`, `bg1`, ...) map to their slot
+    /// under the default color map.
+    pub fn from_scheme_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "dk1" | "tx1" => Self::Dark1,
+            "lt1" | "bg1" => Self::Light1,
+            "dk2" | "tx2" => Self::Dark2,
+            "lt2" | "bg2" => Self::Light2,
+            "accent1" => Self::Accent1,
+            "accent2" => Self::Accent2,
+            "accent3" => Self::Accent3,
+            "accent4" => Self::Accent4,
+            "accent5" => Self::Accent5,
+            "accent6" => Self::Accent6,
+            "hlink" => Self::Hyperlink,
+            "folHlink" => Self::FollowedHyperlink,
+            _ => return None,
+        })
+    }
+
+    /// Parse a WordprocessingML theme color name (`w:color/@w:themeColor`).
+    pub fn from_word_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "dark1" | "text1" => Self::Dark1,
+            "light1" | "background1" => Self::Light1,
+            "dark2" | "text2" => Self::Dark2,
+            "light2" | "background2" => Self::Light2,
+            "hyperlink" => Self::Hyperlink,
+            "followedHyperlink" => Self::FollowedHyperlink,
+            other => return Self::from_scheme_name(other),
+        })
+    }
+
+    /// Get the slot of a SpreadsheetML theme color index (`color/@theme`).
+    ///
+    /// Spreadsheets number the slots with the light and dark colors swapped:
+    /// 0 is `lt1` and 1 is `dk1`.
+    pub fn from_spreadsheet_index(index: u32) -> Option<Self> {
+        Some(match index {
+            0 => Self::Light1,
+            1 => Self::Dark1,
+            2 => Self::Light2,
+            3 => Self::Dark2,
+            4..=11 => Self::ALL[index as usize],
+            _ => return None,
+        })
+    }
+}
+
+/// A DrawingML color transform, with fractional values (1.0 = 100%).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorTransform {
+    /// Mix with white, keeping the given fraction of the color (`a:tint`)
+    Tint(f64),
+    /// Mix with black, keeping the given fraction of the color (`a:shade`)
+    Shade(f64),
+    /// Scale the luminance (`a:lumMod`)
+    LumMod(f64),
+    /// Offset the luminance (`a:lumOff`)
+    LumOff(f64),
+    /// Scale the saturation (`a:satMod`)
+    SatMod(f64),
+}
+
+impl ColorTransform {
+    /// Parse a transform element name and its `val` attribute, in
+    /// thousandths of a percent.
+    fn from_element(name: &[u8], val: i64) -> Option<Self> {
+        let value = val as f64 / 100_000.0;
+        Some(match name {
+            b"tint" => Self::Tint(value),
+            b"shade" => Self::Shade(value),
+            b"lumMod" => Self::LumMod(value),
+            b"lumOff" => Self::LumOff(value),
+            b"satMod" => Self::SatMod(value),
+            _ => return None,
+        })
+    }
+
+    /// Apply the transform to a color.
+    pub fn apply(&self, color: RGBColor) -> RGBColor {
+        apply_transforms(color, std::slice::from_ref(self))
+    }
+
+    /// Apply the transform to RGB channels in the 0.0 to 1.0 range
+    fn apply_to(&self, rgb: [f64; 3]) -> [f64; 3] {
+        match *self {
+            // Tint and shade mix in linear RGB
+            Self::Tint(tint) => map_linear(rgb, |c| c * tint + (1.0 - tint)),
+            Self::Shade(shade) => map_linear(rgb, |c| c * shade),
+            Self::LumMod(m) => map_hsl(rgb, |s, l| (s, l * m)),
+            Self::LumOff(o) => map_hsl(rgb, |s, l| (s, l + o)),
+            Self::SatMod(m) => map_hsl(rgb, |s, l| (s * m, l)),
+        }
+    }
+}
+
+/// Apply transforms in order, rounding to 8-bit channels only at the end
+pub(crate) fn apply_transforms(color: RGBColor, transforms: &[ColorTransform]) -> RGBColor {
+    if transforms.is_empty() {
+        return color;
+    }
+    let rgb = [color.r, color.g, color.b].map(|c| c as f64 / 255.0);
+    let [r, g, b] = transforms
+        .iter()
+        .fold(rgb, |rgb, transform| transform.apply_to(rgb))
+        .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    RGBColor::new(r, g, b)
+}
+
+/// A color as written in a document.
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::common::RGBColor;
+/// use litchi::ooxml::drawings::color::{ColorScheme, ColorSpec, ColorTransform, ThemeColorSlot};
+///
+/// // accent1 at 75% luminance, as Word writes `themeShade="BF"`
+/// let spec = ColorSpec::Theme {
+///     slot: ThemeColorSlot::Accent1,
+///     transforms: vec![ColorTransform::LumMod(0.75)],
+/// };
+/// let color = spec.resolve(&ColorScheme::default()).unwrap();
+/// assert_eq!(color, RGBColor::new(0x2F, 0x55, 0x97));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColorSpec {
+    /// An explicit color, with any transforms already applied
+    Rgb(RGBColor),
+    /// A theme color slot and the transforms to apply to it
+    Theme {
+        /// Theme color scheme slot
+        slot: ThemeColorSlot,
+        /// Transforms applied in order to the slot's color
+        transforms: Vec<ColorTransform>,
+    },
+    /// The application's automatic color (usually black text)
+    Auto,
+}
+
+impl ColorSpec {
+    /// Compute the displayed color against a theme color scheme.
+    ///
+    /// Returns `None` for [`ColorSpec::Auto`], which the application chooses.
+    pub fn resolve(&self, scheme: &ColorScheme) -> Option<RGBColor> {
+        match self {
+            Self::Rgb(color) => Some(*color),
+            Self::Theme { slot, transforms } => {
+                Some(apply_transforms(scheme.get(*slot), transforms))
+            },
+            Self::Auto => None,
+        }
+    }
+
+    /// Parse a SpreadsheetML color as stored by the xlsx styles reader:
+    /// `#AARRGGBB`, `theme:N` with an optional `;tint:T`, `indexed:N` or `auto`.
+    pub fn from_spreadsheet(value: &str) -> Option<Self> {
+        if value == "auto" {
+            return Some(Self::Auto);
+        }
+        if let Some(hex) = value.strip_prefix('#') {
+            // Drop the alpha byte of ARGB values
+            let hex = if hex.len() == 8 { &hex[2..] } else { hex };
+            return RGBColor::from_hex(hex).ok().map(Self::Rgb);
+        }
+        if let Some(index) = value.strip_prefix("indexed:") {
+            let index: usize = index.parse().ok()?;
+            return INDEXED_COLORS
+                .get(index)
+                .map(|&rgb| Self::Rgb(rgb_from_u32(rgb)));
+        }
+
+        let theme = value.strip_prefix("theme:")?;
+        let (index, tint) = match theme.split_once(";tint:") {
+            Some((index, tint)) => (index, tint.parse::<f64>().ok()),
+            None => (theme, None),
+        };
+        let slot = ThemeColorSlot::from_spreadsheet_index(index.parse().ok()?)?;
+        Some(Self::Theme {
+            slot,
+            transforms: tint.map(spreadsheet_tint).unwrap_or_default(),
+        })
+    }
+}
+
+/// Transforms equivalent to a SpreadsheetML `tint` (-1.0 to 1.0), which
+/// darkens or lightens the luminance
+fn spreadsheet_tint(tint: f64) -> Vec<ColorTransform> {
+    if tint < 0.0 {
+        vec![ColorTransform::LumMod(1.0 + tint)]
+    } else if tint > 0.0 {
+        vec![
+            ColorTransform::LumMod(1.0 - tint),
+            ColorTransform::LumOff(tint),
+        ]
+    } else {
+        Vec::new()
+    }
+}
+
+/// Transforms equivalent to WordprocessingML `themeTint` and `themeShade`
+/// values (hex bytes, `FF` meaning unchanged)
+pub(crate) fn word_tint_shade(tint: Option<u8>, shade: Option<u8>) -> Vec<ColorTransform> {
+    let mut transforms = Vec::new();
+    if let Some(tint) = tint.filter(|&t| t != 0xFF) {
+        let tint = tint as f64 / 255.0;
+        transforms.push(ColorTransform::LumMod(tint));
+        transforms.push(ColorTransform::LumOff(1.0 - tint));
+    }
+    if let Some(shade) = shade.filter(|&s| s != 0xFF) {
+        transforms.push(ColorTransform::LumMod(shade as f64 / 255.0));
+    }
+    transforms
+}
+
+/// The colors of a theme's color scheme (`a:clrScheme`).
+///
+/// The default is the Office theme used by Office 2013 and later.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColorScheme {
+    /// Color scheme name
+    pub name: Option<String>,
+    /// Colors indexed in [`ThemeColorSlot::ALL`] order
+    colors: [RGBColor; 12],
+}
+
+impl Default for ColorScheme {
+    fn default() -> Self {
+        Self {
+            name: Some("Office".to_string()),
+            colors: [
+                0x000000, 0xFFFFFF, 0x44546A, 0xE7E6E6, 0x4472C4, 0xED7D31, 0xA5A5A5, 0xFFC000,
+                0x5B9BD5, 0x70AD47, 0x0563C1, 0x954F72,
+            ]
+            .map(rgb_from_u32),
+        }
+    }
+}
+
+impl ColorScheme {
+    /// Get the color of a slot.
+    #[inline]
+    pub fn get(&self, slot: ThemeColorSlot) -> RGBColor {
+        self.colors[slot as usize]
+    }
+
+    /// Set the color of a slot.
+    #[inline]
+    pub fn set(&mut self, slot: ThemeColorSlot, color: RGBColor) {
+        self.colors[slot as usize] = color;
+    }
+
+    /// Parse the color scheme of a theme part (`theme1.xml`).
+    ///
+    /// Slots missing from the theme keep their default Office colors.
+    pub fn from_theme_xml(xml: &[u8]) -> Result<Self> {
+        let mut reader = Reader::from_reader(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut scheme = Self::default();
+        let mut in_scheme = false;
+        let mut slot = None;
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => {
+                    let name = e.local_name();
+                    if name.as_ref() == b"clrScheme" {
+                        in_scheme = true;
+                        scheme.name = attr(&e, b"name");
+                    } else if in_scheme && slot.is_none() {
+                        slot = std::str::from_utf8(name.as_ref())
+                            .ok()
+                            .and_then(ThemeColorSlot::from_scheme_name);
+                    } else if let Some(current) = slot
+                        && let Some(ColorSpec::Rgb(color)) =
+                            read_color_element(&mut reader, &e, true)?
+                    {
+                        scheme.set(current, color);
+                    }
+                },
+                Ok(Event::Empty(e)) => {
+                    if let Some(current) = slot
+                        && let Some(ColorSpec::Rgb(color)) =
+                            read_color_element(&mut reader, &e, false)?
+                    {
+                        scheme.set(current, color);
+                    }
+                },
+                Ok(Event::End(e)) => {
+                    if e.local_name().as_ref() == b"clrScheme" {
+                        break;
+                    }
+                    slot = None;
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(scheme)
+    }
+}
+
+/// Read a DrawingML color element (`a:srgbClr`, `a:schemeClr`, `a:sysClr`
+/// or `a:prstClr`) and its transforms.
+///
+/// `has_children` is true for a start tag, whose content is consumed up to
+/// the matching end tag. Returns `None` for other elements.
+pub(crate) fn read_color_element(
+    reader: &mut Reader<&[u8]>,
+    start: &BytesStart,
+    has_children: bool,
+) -> Result<Option<ColorSpec>> {
+    let name = start.local_name();
+    let val = attr(start, b"val");
+    let base = match name.as_ref() {
+        b"srgbClr" => val
+            .as_deref()
+            .and_then(|v| RGBColor::from_hex(v).ok())
+            .map(Err),
+        b"sysClr" => attr(start, b"lastClr")
+            .and_then(|v| RGBColor::from_hex(&v).ok())
+            .or_else(|| match val.as_deref() {
+                Some("window") => Some(RGBColor::new(0xFF, 0xFF, 0xFF)),
+                Some(_) => Some(RGBColor::new(0, 0, 0)),
+                None => None,
+            })
+            .map(Err),
+        b"prstClr" => val
+            .as_deref()
+            .and_then(|v| RGBColor::from_name(v).ok())
+            .map(Err),
+        b"schemeClr" => val
+            .as_deref()
+            .and_then(ThemeColorSlot::from_scheme_name)
+            .map(Ok),
+        _ => return Ok(None),
+    };
+
+    let mut transforms = Vec::new();
+    if has_children {
+        let end = name.as_ref().to_vec();
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    if let Some(transform) = attr(&e, b"val")
+                        .and_then(|v| v.parse().ok())
+                        .and_then(|v| ColorTransform::from_element(e.local_name().as_ref(), v))
+                    {
+                        transforms.push(transform);
+                    }
+                },
+                Ok(Event::End(e)) if e.local_name().as_ref() == end.as_slice() => break,
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+    }
+
+    Ok(base.map(|base| match base {
+        Ok(slot) => ColorSpec::Theme { slot, transforms },
+        Err(color) => ColorSpec::Rgb(apply_transforms(color, &transforms)),
+    }))
+}
+
+/// Read the color of a fill element (such as `a:solidFill`) whose start tag
+/// was just read, consuming the element.
+pub(crate) fn read_fill_color(
+    reader: &mut Reader<&[u8]>,
+    fill_name: &[u8],
+) -> Result<Option<ColorSpec>> {
+    let mut color = None;
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let spec = read_color_element(reader, &e, true)?;
+                color = color.or(spec);
+            },
+            Ok(Event::Empty(e)) => {
+                let spec = read_color_element(reader, &e, false)?;
+                color = color.or(spec);
+            },
+            Ok(Event::End(e)) if e.local_name().as_ref() == fill_name => break,
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+            _ => {},
+        }
+    }
+    Ok(color)
+}
+
+#[inline]
+fn rgb_from_u32(rgb: u32) -> RGBColor {
+    RGBColor::new((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+}
+
+/// Apply `f` to each channel in linear RGB
+fn map_linear(rgb: [f64; 3], f: impl Fn(f64) -> f64) -> [f64; 3] {
+    rgb.map(|c| {
+        let linear = if c <= 0.04045 {
+            c / 12.92
+        } else {
+            ((c + 0.055) / 1.055).powf(2.4)
+        };
+        let linear = f(linear).clamp(0.0, 1.0);
+        if linear <= 0.0031308 {
+            linear * 12.92
+        } else {
+            1.055 * linear.powf(1.0 / 2.4) - 0.055
+        }
+    })
+}
+
+/// Adjust the saturation and luminance of a color in HSL space
+fn map_hsl(rgb: [f64; 3], f: impl Fn(f64, f64) -> (f64, f64)) -> [f64; 3] {
+    let [r, g, b] = rgb;
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    let (h, s) = if d == 0.0 {
+        (0.0, 0.0)
+    } else {
+        let s = if l > 0.5 {
+            d / (2.0 - max - min)
+        } else {
+            d / (max + min)
+        };
+        let h = if max == r {
+            (g - b) / d + if g < b { 6.0 } else { 0.0 }
+        } else if max == g {
+            (b - r) / d + 2.0
+        } else {
+            (r - g) / d + 4.0
+        };
+        (h / 6.0, s)
+    };
+
+    let (s, l) = f(s, l);
+    let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+    if s == 0.0 {
+        return [l; 3];
+    }
+    let q = if l < 0.5 {
+        l * (1.0 + s)
+    } else {
+        l + s - l * s
+    };
+    let p = 2.0 * l - q;
+    let hue = |t: f64| {
+        let t = t.rem_euclid(1.0);
+        if t < 1.0 / 6.0 {
+            p + (q - p) * 6.0 * t
+        } else if t < 0.5 {
+            q
+        } else if t < 2.0 / 3.0 {
+            p + (q - p) * (2.0 / 3.0 - t) * 6.0
+        } else {
+            p
+        }
+    };
+    [hue(h + 1.0 / 3.0), hue(h), hue(h - 1.0 / 3.0)]
+}
+
+/// The default SpreadsheetML indexed color palette
+const INDEXED_COLORS: [u32; 66] = [
+    0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF, 0x000000,
+    0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF, 0x800000, 0x008000,
+    0x000080, 0x808000, 0x800080, 0x008080, 0xC0C0C0, 0x808080, 0x9999FF, 0x993366, 0xFFFFCC,
+    0xCCFFFF, 0x660066, 0xFF8080, 0x0066CC, 0xCCCCFF, 0x000080, 0xFF00FF, 0xFFFF00, 0x00FFFF,
+    0x800080, 0x800000, 0x008080, 0x0000FF, 0x00CCFF, 0xCCFFFF, 0xCCFFCC, 0xFFFF99, 0x99CCFF,
+    0xFF99CC, 0xCC99FF, 0xFFCC99, 0x3366FF, 0x33CCCC, 0x99CC00, 0xFFCC00, 0xFF9900, 0xFF6600,
+    0x666699, 0x969696, 0x003366, 0x339966, 0x003300, 0x333300, 0x993300, 0x993366, 0x333399,
+    0x333333, // 64: system foreground, 65: system background
+    0x000000, 0xFFFFFF,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_luminance_transforms() {
+        let scheme = ColorScheme::default();
+        let resolve = |slot, transforms| ColorSpec::Theme { slot, transforms }.resolve(&scheme);
+
+        // Word: accent1 with themeShade="BF" is written as 2F5496
+        let color = resolve(ThemeColorSlot::Accent1, word_tint_shade(None, Some(0xBF))).unwrap();
+        assert!(color.r.abs_diff(0x2F) <= 1 && color.g.abs_diff(0x54) <= 1);
+        assert!(color.b.abs_diff(0x96) <= 1);
+
+        // "Accent 1, Lighter 40%"
+        let lighter = resolve(
+            ThemeColorSlot::Accent1,
+            vec![ColorTransform::LumMod(0.6), ColorTransform::LumOff(0.4)],
+        )
+        .unwrap();
+        assert_eq!(lighter.to_hex(), "8FAADC");
+
+        // "White, Darker 15%"
+        let darker = resolve(ThemeColorSlot::Light1, spreadsheet_tint(-0.15)).unwrap();
+        assert_eq!(darker.to_hex(), "D9D9D9");
+    }
+
+    #[test]
+    fn test_tint_shade_and_saturation() {
+        let red = RGBColor::new(0xFF, 0, 0);
+        assert_eq!(ColorTransform::Shade(1.0).apply(red), red);
+        assert_eq!(ColorTransform::Tint(0.0).apply(red).to_hex(), "FFFFFF");
+        assert_eq!(ColorTransform::Shade(0.0).apply(red).to_hex(), "000000");
+        assert_eq!(ColorTransform::Shade(0.5).apply(red).to_hex(), "BC0000");
+        assert_eq!(ColorTransform::SatMod(0.0).apply(red).to_hex(), "808080");
+    }
+
+    #[test]
+    fn test_from_spreadsheet() {
+        assert_eq!(
+            ColorSpec::from_spreadsheet("#FF4472C4"),
+            Some(ColorSpec::Rgb(RGBColor::new(0x44, 0x72, 0xC4)))
+        );
+        assert_eq!(
+            ColorSpec::from_spreadsheet("indexed:10"),
+            Some(ColorSpec::Rgb(RGBColor::new(0xFF, 0, 0)))
+        );
+        assert_eq!(ColorSpec::from_spreadsheet("auto"), Some(ColorSpec::Auto));
+        assert_eq!(
+            ColorSpec::from_spreadsheet("theme:0"),
+            Some(ColorSpec::Theme {
+                slot: ThemeColorSlot::Light1,
+                transforms: Vec::new(),
+            })
+        );
+        assert_eq!(
+            ColorSpec::from_spreadsheet("theme:4;tint:0.5"),
+            Some(ColorSpec::Theme {
+                slot: ThemeColorSlot::Accent1,
+                transforms: vec![ColorTransform::LumMod(0.5), ColorTransform::LumOff(0.5)],
+            })
+        );
+        assert_eq!(ColorSpec::from_spreadsheet("theme:12"), None);
+    }
+
+    #[test]
+    fn test_theme_color_scheme() {
+        let xml = br#"<a:theme xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" name="T">
+            <a:themeElements><a:clrScheme name="Custom">
+                <a:dk1><a:sysClr val="windowText" lastClr="111111"/></a:dk1>
+                <a:lt1><a:sysClr val="window"/></a:lt1>
+                <a:accent1><a:srgbClr val="FF0000"/></a:accent1>
+                <a:accent2><a:srgbClr val="FF0000"><a:lumMod val="50000"/></a:srgbClr></a:accent2>
+            </a:clrScheme>
+            <a:fmtScheme><a:fillStyleLst><a:solidFill><a:schemeClr val="phClr"/></a:solidFill></a:fillStyleLst></a:fmtScheme>
+            </a:themeElements></a:theme>"#;
+        let scheme = ColorScheme::from_theme_xml(xml).unwrap();
+        assert_eq!(scheme.name.as_deref(), Some("Custom"));
+        assert_eq!(scheme.get(ThemeColorSlot::Dark1).to_hex(), "111111");
+        assert_eq!(scheme.get(ThemeColorSlot::Light1).to_hex(), "FFFFFF");
+        assert_eq!(scheme.get(ThemeColorSlot::Accent1).to_hex(), "FF0000");
+        assert_eq!(scheme.get(ThemeColorSlot::Accent2).to_hex(), "800000");
+        // Missing slots keep the Office defaults
+        assert_eq!(scheme.get(ThemeColorSlot::Accent6).to_hex(), "70AD47");
+    }
+
+    #[test]
+    fn test_read_fill_color() {
+        let xml = br#"<a:solidFill><a:schemeClr val="accent2"><a:lumMod val="75000"/><a:alpha val="50000"/></a:schemeClr></a:solidFill>"#;
+        let mut reader = Reader::from_reader(&xml[..]);
+        reader.read_event().unwrap();
+        let color = read_fill_color(&mut reader, b"solidFill").unwrap();
+        assert_eq!(
+            color,
+            Some(ColorSpec::Theme {
+                slot: ThemeColorSlot::Accent2,
+                transforms: vec![ColorTransform::LumMod(0.75)],
+            })
+        );
+    }
+
+    #[test]
+    fn test_slot_names() {
+        assert_eq!(
+            ThemeColorSlot::from_word_name("text1"),
+            Some(ThemeColorSlot::Dark1)
+        );
+        assert_eq!(
+            ThemeColorSlot::from_word_name("accent3"),
+            Some(ThemeColorSlot::Accent3)
+        );
+        assert_eq!(
+            ThemeColorSlot::from_scheme_name("bg2"),
+            Some(ThemeColorSlot::Light2)
+        );
+        assert_eq!(
+            ThemeColorSlot::from_spreadsheet_index(10),
+            Some(ThemeColorSlot::Hyperlink)
+        );
+    }
+}
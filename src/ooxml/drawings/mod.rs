@@ -7,6 +7,7 @@
 //! including shapes, images, and other drawing elements.
 
 pub mod blip;
+pub mod color;
 pub mod ext;
 pub mod fill;
 pub mod xfrm;
@@ -15,6 +15,14 @@ pub enum OoxmlError {
     #[error("XML error: {0}")]
     Xml(String),
 
+    /// Malformed XML at a known position in a package part
+    #[error("XML error in {part} at line {line}: {source}")]
+    MalformedXml {
+        part: String,
+        line: usize,
+        source: quick_xml::Error,
+    },
+
     /// Part not found
     #[error("Part not found: {0}")]
     PartNotFound(String),
@@ -43,11 +51,35 @@ pub enum OoxmlError {
     #[error("Invalid URI: {0}")]
     InvalidUri(String),
 
+    /// The password does not unlock an encrypted package
+    #[error("Incorrect password: {0}")]
+    IncorrectPassword(String),
+
+    /// The package uses an encryption scheme that cannot be decrypted
+    #[error("Unsupported encryption: {0}")]
+    UnsupportedEncryption(String),
+
     /// Generic error
     #[error("{0}")]
     Other(String),
 }
 
+impl OoxmlError {
+    /// Create a [`OoxmlError::MalformedXml`] for an error at byte `position` of `xml`.
+    pub(crate) fn malformed_xml(
+        part: &str,
+        xml: &[u8],
+        position: u64,
+        source: quick_xml::Error,
+    ) -> Self {
+        OoxmlError::MalformedXml {
+            part: part.to_string(),
+            line: crate::common::error::types::line_at(xml, position),
+            source,
+        }
+    }
+}
+
 impl From<quick_xml::Error> for OoxmlError {
     fn from(err: quick_xml::Error) -> Self {
         OoxmlError::Xml(err.to_string())
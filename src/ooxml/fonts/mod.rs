@@ -7,6 +7,8 @@ pub use obfuscation::*;
 #[cfg(feature = "fonts")]
 use crate::common::id::{format_guid_braced, generate_guid_bytes};
 #[cfg(feature = "fonts")]
+use crate::common::trace;
+#[cfg(feature = "fonts")]
 use crate::fonts::{AllsortsSubsetter, CollectGlyphs, FontData, FontSubsetter};
 #[cfg(feature = "fonts")]
 use crate::ooxml::error::{OoxmlError, Result};
@@ -19,6 +21,7 @@ use crate::ooxml::opc::{OpcPackage, PackURI};
 #[cfg(feature = "fonts")]
 use allsorts::{
     binary::read::ReadScope,
+    font::find_good_cmap_subtable,
     tables::{
         FontTableProvider, OpenTypeFont,
         cmap::{Cmap, CmapSubtable},
@@ -45,7 +48,7 @@ fn map_codepoints_to_glyph_ids(font_data: &FontData, codepoints: &[u32]) -> Resu
         .map_err(|e| OoxmlError::Other(format!("Failed to parse font: {}", e)))?;
 
     let provider = font_file
-        .table_provider(font_data.index as usize)
+        .table_provider(font_data.face_index())
         .map_err(|e| OoxmlError::Other(format!("Failed to get table provider: {}", e)))?;
 
     // Get cmap table - table_data returns Result<Option<Rc<dyn AsRef<[u8]>>>>
@@ -59,20 +62,15 @@ fn map_codepoints_to_glyph_ids(font_data: &FontData, codepoints: &[u32]) -> Resu
         .read::<Cmap>()
         .map_err(|e| OoxmlError::Other(format!("Failed to read cmap: {}", e)))?;
 
-    // Find the best cmap subtable (prefer Unicode BMP or full Unicode)
-    // encoding_records() already returns an iterator, iterate directly
-    let mut subtable_opt = None;
-    for record in cmap.encoding_records() {
-        if let Ok(st) = cmap_scope
-            .offset(record.offset as usize)
-            .read::<CmapSubtable>()
-        {
-            subtable_opt = Some(st);
-            break;
-        }
-    }
-
-    let subtable = subtable_opt
+    // Find the best cmap subtable (prefer Unicode BMP or full Unicode), as
+    // code points of a Mac Roman subtable are not Unicode
+    let subtable = find_good_cmap_subtable(&cmap)
+        .and_then(|(_, record)| {
+            cmap_scope
+                .offset(record.offset as usize)
+                .read::<CmapSubtable>()
+                .ok()
+        })
         .ok_or_else(|| OoxmlError::Other("No usable cmap subtable found".to_string()))?;
 
     // Map each code point to glyph ID
@@ -97,6 +95,22 @@ fn map_codepoints_to_glyph_ids(font_data: &FontData, codepoints: &[u32]) -> Resu
     Ok(glyph_ids)
 }
 
+/// The whole face of a font, copied out of its collection and instanced
+/// if it is variable, or the font file as it is if that fails.
+#[cfg(feature = "fonts")]
+fn whole_face(font_data: &FontData) -> Vec<u8> {
+    AllsortsSubsetter::new()
+        .whole_face(font_data)
+        .unwrap_or_else(|_err| {
+            trace::warn!(
+                font = %font_data.name,
+                error = %_err,
+                "could not extract the font face, embedding the font file"
+            );
+            font_data.data.clone()
+        })
+}
+
 /// Information about an embedded font
 #[cfg(feature = "fonts")]
 pub struct EmbeddedFontInfo {
@@ -123,15 +137,27 @@ pub fn embed_fonts_in_package(
         return Ok(embedded_fonts);
     }
 
-    let loader = crate::fonts::loader::FontLoader::new();
+    let mut loader = crate::fonts::loader::FontLoader::new();
+    for (name, data) in &options.registered_fonts {
+        if let Err(_err) = loader.register_font(name, data.clone()) {
+            trace::warn!(font = %name, error = %_err, "registered font is not a valid font");
+        }
+    }
+    for dir in &options.font_dirs {
+        if let Err(_err) = loader.add_font_dir(dir) {
+            trace::warn!(dir = %dir.display(), error = %_err, "could not search font directory");
+        }
+    }
     let subsetter = AllsortsSubsetter::new();
 
     for (font_name, glyphs) in &used_glyphs {
         // 1. Find and load the font
         let font_data = match loader.load_system_font(font_name) {
             Ok(data) => data,
-            Err(_) => {
-                // Font not found on system, skip it
+            Err(_err) => {
+                // Font not registered, in the font directories or on the
+                // system, skip it
+                trace::warn!(font = %font_name, error = %_err, "font not found, not embedding it");
                 continue;
             },
         };
@@ -146,20 +172,30 @@ pub fn embed_fonts_in_package(
                     // Subset the font using the mapped glyph IDs
                     match subsetter.subset(&font_data, &glyph_ids) {
                         Ok(subsetted) => subsetted,
-                        Err(_) => {
+                        Err(_err) => {
                             // If subsetting fails, fall back to full font
-                            font_data.data.clone()
+                            trace::warn!(
+                                font = %font_name,
+                                error = %_err,
+                                "font subsetting failed, embedding the full font"
+                            );
+                            whole_face(&font_data)
                         },
                     }
                 },
-                Err(_) => {
+                Err(_err) => {
                     // If cmap mapping fails, fall back to full font
-                    font_data.data.clone()
+                    trace::warn!(
+                        font = %font_name,
+                        error = %_err,
+                        "glyph mapping failed, embedding the full font"
+                    );
+                    whole_face(&font_data)
                 },
             }
         } else {
             // Subsetting disabled, use full font
-            font_data.data.clone()
+            whole_face(&font_data)
         };
 
         // 3. OOXML Font Obfuscation
@@ -7,6 +7,7 @@
 /// Core properties are stored in the "docProps/core.xml" part of OOXML packages
 /// and contain standard document metadata like title, author, creation date, etc.
 use crate::common::Metadata;
+use crate::ooxml::custom_properties::extract_custom_properties;
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::constants::content_type as ct;
 use crate::ooxml::opc::{OpcPackage, PackURI};
@@ -16,8 +17,9 @@ use quick_xml::events::Event;
 
 /// Extract metadata from an OOXML package.
 ///
-/// This function looks for the core properties part in the OOXML package
-/// and extracts standard document metadata like title, author, creation date, etc.
+/// This function reads the core properties (`docProps/core.xml`), the extended
+/// application properties (`docProps/app.xml`) and the custom properties
+/// (`docProps/custom.xml`) of the package. Missing parts are skipped.
 ///
 /// # Arguments
 ///
@@ -27,41 +29,113 @@ use quick_xml::events::Event;
 ///
 /// A `Metadata` struct containing the extracted document properties
 pub fn extract_metadata(package: &OpcPackage) -> Result<Metadata> {
-    // Find the core properties part
-    let core_part = find_core_properties_part(package)?;
+    let mut metadata =
+        match find_properties_part(package, "/docProps/core.xml", ct::OPC_CORE_PROPERTIES) {
+            Some(core_part) => {
+                let xml_content = std::str::from_utf8(core_part.try_blob()?).map_err(|e| {
+                    OoxmlError::Xml(format!("Invalid UTF-8 in core properties: {}", e))
+                })?;
+                parse_core_properties_xml(xml_content)?
+            },
+            None => Metadata::default(),
+        };
+
+    // The extended and custom parts only add to the core properties, so a
+    // malformed one should not hide what was already read.
+    if let Some(app_part) =
+        find_properties_part(package, "/docProps/app.xml", ct::OFC_EXTENDED_PROPERTIES)
+        && let Ok(xml_content) = std::str::from_utf8(app_part.try_blob()?)
+    {
+        let _ = parse_extended_properties_xml(xml_content, &mut metadata);
+    }
 
-    // Parse the core properties XML
-    let xml_content = std::str::from_utf8(core_part.blob())
-        .map_err(|e| OoxmlError::Xml(format!("Invalid UTF-8 in core properties: {}", e)))?;
+    if let Ok(custom) = extract_custom_properties(package) {
+        metadata.custom_properties.extend(
+            custom
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.clone().into())),
+        );
+    }
 
-    parse_core_properties_xml(xml_content)
+    Ok(metadata)
 }
 
-/// Find the core properties part in an OOXML package.
+/// Find a document properties part in an OOXML package.
 ///
-/// Core properties are typically located at "/docProps/core.xml" and have
-/// the content type "application/vnd.openxmlformats-package.core-properties+xml".
-fn find_core_properties_part(package: &OpcPackage) -> Result<&dyn crate::ooxml::opc::part::Part> {
+/// Properties parts normally live at a fixed location (e.g. `/docProps/core.xml`),
+/// but any part with the matching content type is accepted.
+fn find_properties_part<'a>(
+    package: &'a OpcPackage,
+    uri: &str,
+    content_type: &str,
+) -> Option<&'a dyn crate::ooxml::opc::part::Part> {
     // Try the standard location first
-    let standard_uri = PackURI::new("/docProps/core.xml")
-        .map_err(|e| OoxmlError::Other(format!("Invalid core properties URI: {}", e)))?;
-
-    if let Ok(part) = package.get_part(&standard_uri)
-        && part.content_type() == ct::OPC_CORE_PROPERTIES
+    if let Ok(standard_uri) = PackURI::new(uri)
+        && let Ok(part) = package.get_part(&standard_uri)
+        && part.content_type() == content_type
     {
-        return Ok(part);
+        return Some(part);
     }
 
-    // Fallback: search through all parts for core properties content type
-    for part in package.iter_parts() {
-        if part.content_type() == ct::OPC_CORE_PROPERTIES {
-            return Ok(part);
+    // Fallback: search through all parts for the content type
+    package
+        .iter_parts()
+        .find(|part| part.content_type() == content_type)
+}
+
+/// Parse extended (application) properties XML into `metadata`.
+///
+/// Extended properties hold application-specific statistics and the
+/// company/manager fields that are not part of the core properties.
+fn parse_extended_properties_xml(xml: &str, metadata: &mut Metadata) -> Result<()> {
+    let mut reader = Reader::from_reader(xml.as_bytes());
+    reader.config_mut().trim_text(true);
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => {
+                let field = match e.local_name().as_ref() {
+                    b"Application" => &mut metadata.application,
+                    b"Company" => &mut metadata.company,
+                    b"Manager" => &mut metadata.manager,
+                    b"Template" => &mut metadata.template,
+                    b"Pages" | b"Slides" => {
+                        if let Some(text) = read_text_element(&mut reader)? {
+                            metadata.page_count = text.trim().parse().ok();
+                        }
+                        continue;
+                    },
+                    b"Words" => {
+                        if let Some(text) = read_text_element(&mut reader)? {
+                            metadata.word_count = text.trim().parse().ok();
+                        }
+                        continue;
+                    },
+                    b"Characters" => {
+                        if let Some(text) = read_text_element(&mut reader)? {
+                            metadata.character_count = text.trim().parse().ok();
+                        }
+                        continue;
+                    },
+                    b"DocSecurity" => {
+                        if let Some(text) = read_text_element(&mut reader)? {
+                            metadata.security = text.trim().parse().ok();
+                        }
+                        continue;
+                    },
+                    _ => continue,
+                };
+                if let Some(text) = read_text_element(&mut reader)? {
+                    *field = Some(text);
+                }
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OoxmlError::Xml(format!("XML parsing error: {}", e))),
+            _ => {},
         }
     }
 
-    Err(OoxmlError::PartNotFound(
-        "Core properties part not found".to_string(),
-    ))
+    Ok(())
 }
 
 /// Parse core properties XML and extract metadata.
@@ -103,6 +177,11 @@ fn parse_core_properties_xml(xml: &str) -> Result<Metadata> {
                             metadata.description = Some(text);
                         }
                     },
+                    b"dc:language" => {
+                        if let Some(text) = read_text_element(&mut reader)? {
+                            metadata.language = Some(text);
+                        }
+                    },
                     b"cp:lastModifiedBy" => {
                         if let Some(text) = read_text_element(&mut reader)? {
                             metadata.last_modified_by = Some(text);
@@ -197,7 +276,7 @@ fn read_text_element(reader: &mut Reader<&[u8]>) -> Result<Option<String>> {
 /// - 2023-10-10T14:30:00Z
 /// - 2023-10-10T14:30:00.1234567Z
 /// - 2023-10-10T14:30:00
-fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
+pub(crate) fn parse_datetime(s: &str) -> Result<DateTime<Utc>> {
     // Try parsing with different formats
     if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
         return Ok(dt.with_timezone(&Utc));
@@ -253,6 +332,37 @@ mod tests {
         assert_eq!(dt.year(), 2023);
     }
 
+    #[test]
+    fn test_parse_extended_properties_xml() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Properties xmlns="http://schemas.openxmlformats.org/officeDocument/2006/extended-properties"
+            xmlns:vt="http://schemas.openxmlformats.org/officeDocument/2006/docPropsVTypes">
+    <Template>Normal.dotm</Template>
+    <Pages>3</Pages>
+    <Words>120</Words>
+    <Characters>700</Characters>
+    <Application>Microsoft Office Word</Application>
+    <DocSecurity>0</DocSecurity>
+    <HeadingPairs><vt:vector size="1" baseType="variant"><vt:variant><vt:lpstr>Title</vt:lpstr></vt:variant></vt:vector></HeadingPairs>
+    <Manager>Jane</Manager>
+    <Company>Example Corp</Company>
+</Properties>"#;
+
+        let mut metadata = Metadata::default();
+        parse_extended_properties_xml(xml, &mut metadata).unwrap();
+        assert_eq!(metadata.template, Some("Normal.dotm".to_string()));
+        assert_eq!(metadata.page_count, Some(3));
+        assert_eq!(metadata.word_count, Some(120));
+        assert_eq!(metadata.character_count, Some(700));
+        assert_eq!(
+            metadata.application,
+            Some("Microsoft Office Word".to_string())
+        );
+        assert_eq!(metadata.security, Some(0));
+        assert_eq!(metadata.manager, Some("Jane".to_string()));
+        assert_eq!(metadata.company, Some("Example Corp".to_string()));
+    }
+
     #[test]
     fn test_parse_core_properties_xml() {
         let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
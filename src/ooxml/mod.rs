@@ -19,6 +19,7 @@
 //!    - `xlsb`: Excel binary spreadsheets
 //!    - `pptx`: PowerPoint presentations (placeholder)
 //!    - `metadata`: Core properties/metadata extraction
+//!    - `signatures`: Digital signature detection and digest checks (`signatures` feature)
 //!
 //! # Example: Working with Word Documents
 //!
@@ -54,6 +55,9 @@ pub mod crypto;
 #[cfg(feature = "fonts")]
 pub mod fonts;
 
+#[cfg(feature = "signatures")]
+pub mod signatures;
+
 // Re-export commonly used types from OPC layer
 pub use opc::{OpcPackage, PackURI};
 
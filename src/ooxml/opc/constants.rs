@@ -63,6 +63,8 @@ pub mod content_type {
         "application/vnd.openxmlformats-officedocument.wordprocessingml.document.glossary+xml";
     pub const WML_DOCUMENT_MAIN: &str =
         "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml";
+    pub const WML_DOCUMENT_MACRO_MAIN: &str =
+        "application/vnd.ms-word.document.macroEnabled.main+xml";
     pub const WML_ENDNOTES: &str =
         "application/vnd.openxmlformats-officedocument.wordprocessingml.endnotes+xml";
     pub const WML_FONT_TABLE: &str =
@@ -98,6 +100,8 @@ pub mod content_type {
         "application/vnd.openxmlformats-officedocument.spreadsheetml.pivotCacheRecords+xml";
     pub const SML_PIVOT_TABLE: &str =
         "application/vnd.openxmlformats-officedocument.spreadsheetml.pivotTable+xml";
+    pub const SML_TABLE: &str =
+        "application/vnd.openxmlformats-officedocument.spreadsheetml.table+xml";
     pub const SML_COMMENTS: &str =
         "application/vnd.openxmlformats-officedocument.spreadsheetml.comments+xml";
     pub const SML_THREADED_COMMENTS: &str = "application/vnd.ms-excel.threadedcomments+xml";
@@ -193,6 +197,14 @@ pub mod relationship_type {
     pub const THUMBNAIL: &str =
         "http://schemas.openxmlformats.org/package/2006/relationships/metadata/thumbnail";
 
+    // Digital signatures
+    pub const DIGITAL_SIGNATURE_ORIGIN: &str =
+        "http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/origin";
+    pub const DIGITAL_SIGNATURE: &str =
+        "http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/signature";
+    pub const DIGITAL_SIGNATURE_CERTIFICATE: &str =
+        "http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/certificate";
+
     // Office document
     pub const OFFICE_DOCUMENT: &str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument";
@@ -226,11 +238,21 @@ pub mod relationship_type {
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotCacheRecords";
     pub const PIVOT_TABLE: &str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/pivotTable";
+    pub const TABLE: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/table";
     pub const THREADED_COMMENTS: &str =
         "http://schemas.microsoft.com/office/2017/10/relationships/threadedComment";
     pub const PERSONS: &str = "http://schemas.microsoft.com/office/2017/10/relationships/person";
     pub const SHEET_METADATA: &str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/sheetMetadata";
+    pub const WORKSHEET: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/worksheet";
+    pub const CHARTSHEET: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/chartsheet";
+    pub const SHARED_STRINGS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/sharedStrings";
+    pub const CALC_CHAIN: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/calcChain";
 
     // Images and media
     pub const IMAGE: &str =
@@ -259,6 +281,12 @@ pub mod relationship_type {
     // Custom XML
     pub const CUSTOM_XML: &str =
         "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXml";
+    pub const CUSTOM_XML_PROPS: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/customXmlProps";
+
+    // VBA projects
+    pub const VBA_PROJECT: &str =
+        "http://schemas.microsoft.com/office/2006/relationships/vbaProject";
 
     // External links
     pub const HYPERLINK: &str =
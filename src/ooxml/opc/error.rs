@@ -24,8 +24,15 @@ pub enum OpcError {
     #[error("XML parsing error: {0}")]
     XmlError(String),
 
+    #[error("XML parsing error in {part} at line {line}: {source}")]
+    MalformedXml {
+        part: String,
+        line: usize,
+        source: quick_xml::Error,
+    },
+
     #[error("ZIP error: {0}")]
-    ZipError(String),
+    ZipError(#[from] soapberry_zip::Error),
 
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
@@ -43,9 +50,19 @@ pub enum OpcError {
     AttrError(String),
 }
 
-impl From<soapberry_zip::Error> for OpcError {
-    fn from(err: soapberry_zip::Error) -> Self {
-        OpcError::ZipError(err.to_string())
+impl OpcError {
+    /// Create a [`OpcError::MalformedXml`] for an error at byte `position` of `xml`.
+    pub(crate) fn malformed_xml(
+        part: &str,
+        xml: &[u8],
+        position: u64,
+        source: quick_xml::Error,
+    ) -> Self {
+        OpcError::MalformedXml {
+            part: part.to_string(),
+            line: crate::common::error::types::line_at(xml, position),
+            source,
+        }
     }
 }
 
@@ -25,6 +25,7 @@ pub mod phys_pkg;
 pub mod pkgreader;
 pub mod pkgwriter;
 pub mod rel;
+pub mod validation;
 
 // Re-export commonly used types
 pub use package::OpcPackage;
@@ -32,3 +33,4 @@ pub use packuri::PackURI;
 pub use part::{BlobPart, Part, XmlPart};
 pub use pkgwriter::PackageWriter;
 pub use rel::{Relationship, Relationships};
+pub use validation::{IssueKind, Severity, ValidationIssue, validate, validate_bytes};
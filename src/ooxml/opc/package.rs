@@ -4,16 +4,21 @@
 //! Convention package in memory. It manages parts, relationships, and provides
 //! high-level operations for working with office documents.
 
+use crate::common::trace;
 use crate::ooxml::opc::constants::relationship_type;
 use crate::ooxml::opc::error::{OpcError, Result};
 use crate::ooxml::opc::packuri::{PACKAGE_URI, PackURI};
-use crate::ooxml::opc::part::{Part, PartFactory};
+use crate::ooxml::opc::part::{DeferredPart, Part, PartFactory};
 use crate::ooxml::opc::phys_pkg::{OwnedPhysPkgReader, PhysPkgReader};
 use crate::ooxml::opc::pkgreader::PackageReader;
 use crate::ooxml::opc::rel::Relationships;
-use std::collections::HashMap;
+use soapberry_zip::office::FileArchive;
+use std::collections::{HashMap, HashSet};
 use std::io::Read;
 use std::path::Path;
+#[cfg(feature = "fonts")]
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
 
 /// Options for saving an OPC package.
 #[derive(Debug, Clone, Default)]
@@ -22,6 +27,18 @@ pub struct SaveOptions {
     pub embed_fonts: bool,
     /// Whether to subset fonts (only embed used glyphs).
     pub subset_fonts: bool,
+    /// Fonts to embed registered by family name, searched before
+    /// `font_dirs` and the system fonts.
+    #[cfg(feature = "fonts")]
+    pub registered_fonts: Vec<(String, Arc<Vec<u8>>)>,
+    /// Directories searched for fonts to embed before the system fonts.
+    #[cfg(feature = "fonts")]
+    pub font_dirs: Vec<PathBuf>,
+    /// Whether to drop the digital signatures of a signed package.
+    ///
+    /// Signatures are kept by default, but they no longer match once a
+    /// signed part changes, and saving such a package logs a warning.
+    pub strip_signatures: bool,
 }
 
 /// Main API class for working with OPC packages.
@@ -40,6 +57,31 @@ pub struct OpcPackage {
 
     /// Save preferences
     save_options: SaveOptions,
+
+    /// The file this package was opened from, if it was opened from a path
+    source: Option<PackageSource>,
+
+    /// Whether the package was damaged and opened in recovery mode
+    recovered: bool,
+}
+
+/// Parts of packages opened from a path whose content is at least this many
+/// bytes are left in the file until they are read, see [`Part::is_deferred`].
+pub(crate) const DEFERRED_PART_SIZE: u64 = 16 * 1024 * 1024;
+
+/// The file a package was opened from.
+///
+/// Saving copies the parts that still hold their original content straight
+/// from this file instead of recompressing them. Only the open file handle is
+/// kept; the archive is read again when the package is saved.
+struct PackageSource {
+    /// The original ZIP archive, read from its file on demand. Deferred
+    /// parts share it to read their content.
+    archive: Arc<FileArchive>,
+
+    /// The content each part was loaded with. A part is unmodified for as
+    /// long as it still shares this allocation.
+    blobs: HashMap<PackURI, Weak<Vec<u8>>>,
 }
 
 impl std::fmt::Debug for OpcPackage {
@@ -59,6 +101,8 @@ impl OpcPackage {
             rels: Relationships::new(PACKAGE_URI.to_string()),
             parts: HashMap::new(),
             save_options: SaveOptions::default(),
+            source: None,
+            recovered: false,
         }
     }
 
@@ -79,8 +123,33 @@ impl OpcPackage {
         self
     }
 
+    /// Register font data to embed under a family name, for fonts that are
+    /// shipped with the application instead of installed.
+    ///
+    /// Registered fonts are searched first, then the directories added
+    /// with [`add_font_dir`](Self::add_font_dir), then the system fonts.
+    #[cfg(feature = "fonts")]
+    pub fn register_font(&mut self, name: &str, data: impl Into<Arc<Vec<u8>>>) -> &mut Self {
+        self.save_options
+            .registered_fonts
+            .push((name.to_string(), data.into()));
+        self
+    }
+
+    /// Search a directory for fonts to embed before the system fonts.
+    #[cfg(feature = "fonts")]
+    pub fn add_font_dir(&mut self, dir: impl Into<PathBuf>) -> &mut Self {
+        self.save_options.font_dirs.push(dir.into());
+        self
+    }
+
     /// Open an OPC package from a file.
     ///
+    /// The file is kept open so that saving can copy unmodified parts from it
+    /// without recompressing them, and parts larger than 16 MiB are only read
+    /// from it when they are accessed. It should therefore not be changed by
+    /// other programs while the package is open.
+    ///
     /// # Arguments
     /// * `path` - Path to the package file (.docx, .xlsx, .pptx, etc.)
     ///
@@ -94,10 +163,36 @@ impl OpcPackage {
     /// let pkg = OpcPackage::open("document.docx").unwrap();
     /// ```
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let owned_reader = OwnedPhysPkgReader::open(path)?;
-        let phys_reader = owned_reader.reader()?;
-        let pkg_reader = PackageReader::from_phys_reader(&phys_reader)?;
-        Self::unmarshal(pkg_reader)
+        Self::open_deferring(path, DEFERRED_PART_SIZE)
+    }
+
+    /// Open an OPC package from a file, leaving parts of at least `min_size`
+    /// bytes in the file until they are read.
+    pub(crate) fn open_deferring<P: AsRef<Path>>(path: P, min_size: u64) -> Result<Self> {
+        let path = path.as_ref();
+        if !path.exists() {
+            return Err(OpcError::PackageNotFound(path.display().to_string()));
+        }
+
+        let file = std::fs::File::open(path)?;
+        let _span = trace::info_span!(
+            "open_package",
+            format = "opc",
+            size = file.metadata()?.len()
+        )
+        .entered();
+        let archive = Arc::new(FileArchive::from_file(file)?);
+        let pkg_reader = PackageReader::from_file_archive(&archive, min_size)?;
+        let mut package = Self::unmarshal(pkg_reader, Some(&archive))?;
+
+        let blobs = package
+            .parts
+            .values()
+            .filter(|part| !part.is_deferred())
+            .map(|part| (part.partname().clone(), Arc::downgrade(&part.blob_arc())))
+            .collect();
+        package.source = Some(PackageSource { archive, blobs });
+        Ok(package)
     }
 
     /// Load an OPC package from a reader.
@@ -106,9 +201,7 @@ impl OpcPackage {
     /// * `reader` - A reader that implements Read
     pub fn from_reader<R: Read>(reader: R) -> Result<Self> {
         let owned_reader = OwnedPhysPkgReader::from_reader(reader)?;
-        let phys_reader = owned_reader.reader()?;
-        let pkg_reader = PackageReader::from_phys_reader(&phys_reader)?;
-        Self::unmarshal(pkg_reader)
+        Self::from_bytes(owned_reader.as_bytes())
     }
 
     /// Load an OPC package from a byte slice.
@@ -116,9 +209,44 @@ impl OpcPackage {
     /// # Arguments
     /// * `data` - The ZIP archive data as a byte slice
     pub fn from_bytes(data: &[u8]) -> Result<Self> {
+        let _span = trace::info_span!("open_package", format = "opc", size = data.len()).entered();
         let phys_reader = PhysPkgReader::new(data)?;
         let pkg_reader = PackageReader::from_phys_reader(&phys_reader)?;
-        Self::unmarshal(pkg_reader)
+        Self::unmarshal(pkg_reader, None)
+    }
+
+    /// Load a possibly damaged OPC package from a byte slice.
+    ///
+    /// This mirrors what Word does when it repairs a file: if the ZIP central
+    /// directory is unreadable (for example because the file was truncated)
+    /// the parts are found by scanning local file headers, and if
+    /// `[Content_Types].xml` is missing or malformed the content types are
+    /// inferred from part names and relationships. Parts that cannot be read
+    /// are dropped.
+    ///
+    /// Intact packages load exactly as with [`from_bytes`](Self::from_bytes);
+    /// [`was_recovered`](Self::was_recovered) tells whether anything had to be
+    /// repaired.
+    ///
+    /// # Arguments
+    /// * `data` - The ZIP archive data as a byte slice
+    pub fn recover(data: &[u8]) -> Result<Self> {
+        let _span = trace::info_span!("open_package", format = "opc", size = data.len()).entered();
+        let phys_reader = PhysPkgReader::recover(data)?;
+        let pkg_reader = PackageReader::recover(&phys_reader)?;
+        let recovered = pkg_reader.is_recovered();
+        let mut package = Self::unmarshal(pkg_reader, None)?;
+        package.recovered = recovered;
+        Ok(package)
+    }
+
+    /// Whether the package was damaged and had to be repaired when it was
+    /// opened with [`recover`](Self::recover).
+    ///
+    /// Repaired packages may be missing content, so callers should warn users
+    /// before saving over the original file.
+    pub fn was_recovered(&self) -> bool {
+        self.recovered
     }
 
     /// Unmarshal a package from a package reader.
@@ -127,7 +255,8 @@ impl OpcPackage {
     /// and relationships into the in-memory object graph.
     ///
     /// Optimized to minimize clones by consuming the package reader and moving data.
-    fn unmarshal(mut pkg_reader: PackageReader) -> Result<Self> {
+    /// Deferred parts read their content from `source`.
+    fn unmarshal(mut pkg_reader: PackageReader, source: Option<&Arc<FileArchive>>) -> Result<Self> {
         let mut package = Self::new();
 
         // Get ownership of package relationships and parts
@@ -141,11 +270,18 @@ impl OpcPackage {
         // Create all parts - move data instead of cloning
         for spart in sparts {
             let partname = spart.partname.clone(); // Need to clone partname for the HashMap key
-            let mut part = PartFactory::load(
-                spart.partname,     // Move
-                spart.content_type, // Move
-                spart.blob,         // Move (blob is Arc internally if large)
-            )?;
+            let mut part: Box<dyn Part + Send + Sync> = match source {
+                Some(source) if spart.deferred => Box::new(DeferredPart::new(
+                    spart.partname,
+                    spart.content_type,
+                    Arc::clone(source),
+                )),
+                _ => PartFactory::load(
+                    spart.partname,     // Move
+                    spart.content_type, // Move
+                    spart.blob,         // Move (blob is Arc internally if large)
+                )?,
+            };
 
             // Load part relationships
             for srel in spart.srels {
@@ -189,6 +325,81 @@ impl OpcPackage {
         self.get_part(&partname)
     }
 
+    /// Get the VBA project part (`vbaProject.bin`) of a macro-enabled package.
+    ///
+    /// The part is found through the `vbaProject` relationship of the main
+    /// document part; its blob is a compound file.
+    pub fn vba_project_part(&self) -> Option<&dyn Part> {
+        let main_part = self.main_document_part().ok()?;
+        let rel = main_part
+            .rels()
+            .part_with_reltype(relationship_type::VBA_PROJECT)
+            .ok()?;
+        self.get_part(&rel.target_partname().ok()?).ok()
+    }
+
+    /// Get the XML signature parts of a digitally signed package.
+    ///
+    /// Signatures are found through the `digital-signature/origin`
+    /// relationship of the package and the `digital-signature/signature`
+    /// relationships of the origin part.
+    pub fn signature_parts(&self) -> Vec<&dyn Part> {
+        let Ok(origin) = self.part_by_reltype(relationship_type::DIGITAL_SIGNATURE_ORIGIN) else {
+            return Vec::new();
+        };
+        origin
+            .rels()
+            .iter()
+            .filter(|rel| rel.reltype() == relationship_type::DIGITAL_SIGNATURE)
+            .filter_map(|rel| self.get_part(&rel.target_partname().ok()?).ok())
+            .collect()
+    }
+
+    /// Whether the package carries at least one digital signature.
+    pub fn is_signed(&self) -> bool {
+        !self.signature_parts().is_empty()
+    }
+
+    /// Get the partnames of the signature origin, the XML signatures and
+    /// the certificates they reference.
+    pub(crate) fn signature_partnames(&self) -> HashSet<PackURI> {
+        let Ok(origin) = self.part_by_reltype(relationship_type::DIGITAL_SIGNATURE_ORIGIN) else {
+            return HashSet::new();
+        };
+        let mut partnames = HashSet::from([origin.partname().clone()]);
+        for signature in self.signature_parts() {
+            partnames.insert(signature.partname().clone());
+            partnames.extend(
+                signature
+                    .rels()
+                    .iter()
+                    .filter(|rel| rel.reltype() == relationship_type::DIGITAL_SIGNATURE_CERTIFICATE)
+                    .filter_map(|rel| rel.target_partname().ok()),
+            );
+        }
+        partnames
+    }
+
+    /// Remove all digital signatures from the package.
+    ///
+    /// Any change to a signed part breaks its signatures, so edited packages
+    /// should be saved without them. See also
+    /// [`SaveOptions::strip_signatures`].
+    pub fn remove_signatures(&mut self) {
+        for partname in self.signature_partnames() {
+            self.parts.remove(&partname);
+        }
+        let origin_rels: Vec<String> = self
+            .rels
+            .iter()
+            .filter(|rel| rel.reltype() == relationship_type::DIGITAL_SIGNATURE_ORIGIN)
+            .map(|rel| rel.r_id().to_string())
+            .collect();
+        for r_id in origin_rels {
+            self.rels.remove(&r_id);
+        }
+    }
+
     /// Get a part by its partname.
     ///
     /// # Arguments
@@ -337,10 +548,35 @@ impl OpcPackage {
         self.parts.contains_key(partname)
     }
 
+    /// Get the archive this package was opened from.
+    ///
+    /// Only packages opened from a path with [`OpcPackage::open`] keep their
+    /// source archive.
+    pub(crate) fn source_archive(&self) -> Option<&FileArchive> {
+        self.source.as_ref().map(|source| source.archive.as_ref())
+    }
+
+    /// Check whether a part still holds the content it was loaded with.
+    ///
+    /// Always false for packages without a source archive. Whether the
+    /// source file still holds that content is checked when the part is
+    /// copied from it on save.
+    pub(crate) fn is_part_unmodified(&self, part: &dyn Part) -> bool {
+        self.source.as_ref().is_some_and(|source| {
+            part.is_deferred()
+                || source
+                    .blobs
+                    .get(part.partname())
+                    .is_some_and(|blob| std::ptr::eq(blob.as_ptr(), Arc::as_ptr(&part.blob_arc())))
+        })
+    }
+
     /// Save the package to a file.
     ///
     /// Writes the complete OPC package including all parts, relationships,
-    /// and content types to a ZIP file.
+    /// and content types to a ZIP file. When the package was opened from a
+    /// file and most parts are unmodified, those parts are copied from the
+    /// original archive without being recompressed.
     ///
     /// # Arguments
     /// * `path` - Path where the package should be written
@@ -456,4 +692,244 @@ mod tests {
             "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"
         );
     }
+
+    fn create_docx_with_media() -> Vec<u8> {
+        let mut writer = StreamingArchiveWriter::new();
+        writer
+            .write_deflated(
+                "[Content_Types].xml",
+                br#"<?xml version="1.0"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+    <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+    <Default Extension="xml" ContentType="application/xml"/>
+    <Default Extension="png" ContentType="image/png"/>
+    <Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+</Types>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "_rels/.rels",
+                br#"<?xml version="1.0"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+</Relationships>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "word/document.xml",
+                br#"<?xml version="1.0"?><document><body/></document>"#,
+            )
+            .unwrap();
+
+        let mut rels = String::from(
+            r#"<?xml version="1.0"?><Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">"#,
+        );
+        for i in 1..=3u8 {
+            rels.push_str(&format!(
+                r#"<Relationship Id="rId{i}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/image" Target="media/image{i}.png"/>"#
+            ));
+            let image: Vec<u8> = (0..4096u32)
+                .map(|n| (n * u32::from(i) % 251) as u8)
+                .collect();
+            writer
+                .write_deflated(&format!("word/media/image{i}.png"), &image)
+                .unwrap();
+        }
+        rels.push_str("</Relationships>");
+        writer
+            .write_deflated("word/_rels/document.xml.rels", rels.as_bytes())
+            .unwrap();
+        writer
+            .write_deflated("customXml/orphan.dat", b"<orphan/>")
+            .unwrap();
+
+        writer.finish_to_bytes().unwrap()
+    }
+
+    fn compressed_member(data: &[u8], name: &str) -> Vec<u8> {
+        let archive = soapberry_zip::ZipArchive::from_slice(data).unwrap();
+        let entry = archive
+            .entries()
+            .map(|e| e.unwrap())
+            .find(|e| e.file_path().as_ref() == name.as_bytes())
+            .unwrap();
+        archive
+            .get_entry(entry.wayfinder())
+            .unwrap()
+            .data()
+            .to_vec()
+    }
+
+    #[test]
+    fn test_save_copies_unmodified_parts() {
+        let zip_data = create_docx_with_media();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &zip_data).unwrap();
+        let mut pkg = OpcPackage::open(file.path()).unwrap();
+
+        let document = PackURI::new("/word/document.xml").unwrap();
+        let image = PackURI::new("/word/media/image2.png").unwrap();
+        pkg.get_part_mut(&document)
+            .unwrap()
+            .set_blob(br#"<?xml version="1.0"?><document><body>edited</body></document>"#.to_vec());
+        assert!(!pkg.is_part_unmodified(pkg.get_part(&document).unwrap()));
+        assert!(pkg.is_part_unmodified(pkg.get_part(&image).unwrap()));
+
+        let saved = crate::ooxml::opc::pkgwriter::PackageWriter::to_bytes(&pkg).unwrap();
+        assert_eq!(
+            compressed_member(&saved, "word/media/image2.png"),
+            compressed_member(&zip_data, "word/media/image2.png")
+        );
+
+        let reopened = OpcPackage::from_bytes(&saved).unwrap();
+        assert_eq!(reopened.part_count(), pkg.part_count());
+        assert_eq!(
+            reopened.get_part(&document).unwrap().blob(),
+            pkg.get_part(&document).unwrap().blob()
+        );
+        assert_eq!(
+            reopened.get_part(&image).unwrap().blob(),
+            pkg.get_part(&image).unwrap().blob()
+        );
+        assert_eq!(reopened.get_part(&document).unwrap().rels().len(), 3);
+
+        // Members that are not parts of the package are dropped
+        let archive = soapberry_zip::office::ArchiveReader::new(&saved).unwrap();
+        assert!(!archive.contains("customXml/orphan.dat"));
+
+        // Saving over the source file keeps the package usable
+        pkg.save(file.path()).unwrap();
+        let resaved = crate::ooxml::opc::pkgwriter::PackageWriter::to_bytes(&pkg).unwrap();
+        assert_eq!(
+            compressed_member(&resaved, "word/media/image2.png"),
+            compressed_member(&zip_data, "word/media/image2.png")
+        );
+        assert_eq!(
+            OpcPackage::from_bytes(&resaved).unwrap().part_count(),
+            pkg.part_count()
+        );
+
+        // A source file truncated before writing falls back to a full rewrite
+        pkg.to_stream(std::fs::File::create(file.path()).unwrap())
+            .unwrap();
+        let reopened = OpcPackage::open(file.path()).unwrap();
+        assert_eq!(
+            reopened.get_part(&document).unwrap().blob(),
+            pkg.get_part(&document).unwrap().blob()
+        );
+    }
+
+    #[test]
+    fn test_save_copies_deferred_parts() {
+        let zip_data = create_docx_with_media();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &zip_data).unwrap();
+        let mut pkg = OpcPackage::open_deferring(file.path(), 1000).unwrap();
+
+        let document = PackURI::new("/word/document.xml").unwrap();
+        let image = PackURI::new("/word/media/image2.png").unwrap();
+        assert!(!pkg.get_part(&document).unwrap().is_deferred());
+        assert!(pkg.get_part(&image).unwrap().is_deferred());
+
+        let mut streamed = Vec::new();
+        let image_part = pkg.get_part(&image).unwrap();
+        image_part
+            .content_reader()
+            .unwrap()
+            .read_to_end(&mut streamed)
+            .unwrap();
+        assert_eq!(streamed.len(), 4096);
+        assert!(image_part.is_deferred());
+
+        pkg.get_part_mut(&document)
+            .unwrap()
+            .set_blob(br#"<?xml version="1.0"?><document><body>edited</body></document>"#.to_vec());
+        let saved = crate::ooxml::opc::pkgwriter::PackageWriter::to_bytes(&pkg).unwrap();
+        assert_eq!(
+            compressed_member(&saved, "word/media/image2.png"),
+            compressed_member(&zip_data, "word/media/image2.png")
+        );
+        assert!(pkg.get_part(&image).unwrap().is_deferred());
+
+        // Without a readable source a deferred part cannot be written
+        let partnames: Vec<PackURI> = pkg.iter_parts().map(|p| p.partname().clone()).collect();
+        for partname in partnames.iter().filter(|partname| **partname != image) {
+            let part = pkg.get_part_mut(partname).unwrap();
+            let blob = part.blob().to_vec();
+            part.set_blob(blob);
+        }
+        std::fs::write(file.path(), b"").unwrap();
+        assert!(crate::ooxml::opc::pkgwriter::PackageWriter::to_bytes(&pkg).is_err());
+        assert!(pkg.get_part(&image).unwrap().try_blob().is_err());
+        assert!(pkg.get_part(&image).unwrap().is_deferred());
+
+        // Reading the content keeps it in the part
+        std::fs::write(file.path(), &zip_data).unwrap();
+        assert_eq!(pkg.get_part(&image).unwrap().blob(), streamed);
+        assert!(!pkg.get_part(&image).unwrap().is_deferred());
+    }
+
+    #[test]
+    fn test_save_rechecks_changed_source() {
+        let zip_data = create_docx_with_media();
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), &zip_data).unwrap();
+        let pkg = OpcPackage::open_deferring(file.path(), 1000).unwrap();
+        let document = PackURI::new("/word/document.xml").unwrap();
+        let image = PackURI::new("/word/media/image2.png").unwrap();
+        let original = pkg.get_part(&document).unwrap().blob().to_vec();
+
+        // A part replaced in the file after opening is written from memory
+        let mut editor = soapberry_zip::office::ArchiveEditor::from_slice(&zip_data).unwrap();
+        editor.write_deflated("word/document.xml", b"<foreign/>".to_vec());
+        let foreign = editor.finish_to_bytes().unwrap();
+        std::fs::write(file.path(), &foreign).unwrap();
+        let saved = crate::ooxml::opc::pkgwriter::PackageWriter::to_bytes(&pkg).unwrap();
+        let reopened = OpcPackage::from_bytes(&saved).unwrap();
+        assert_eq!(reopened.get_part(&document).unwrap().blob(), original);
+        assert_eq!(
+            compressed_member(&saved, "word/media/image2.png"),
+            compressed_member(&zip_data, "word/media/image2.png")
+        );
+
+        // A deferred part has nothing to fall back on
+        let mut editor = soapberry_zip::office::ArchiveEditor::from_slice(&foreign).unwrap();
+        editor.write_deflated("word/media/image2.png", vec![1u8; 4096]);
+        std::fs::write(file.path(), editor.finish_to_bytes().unwrap()).unwrap();
+        assert!(crate::ooxml::opc::pkgwriter::PackageWriter::to_bytes(&pkg).is_err());
+        assert!(pkg.get_part(&image).unwrap().try_blob().is_err());
+    }
+
+    #[test]
+    fn test_from_reader_keeps_no_source() {
+        let pkg = OpcPackage::from_reader(Cursor::new(create_docx_with_media())).unwrap();
+        assert!(pkg.source_archive().is_none());
+        let image = PackURI::new("/word/media/image2.png").unwrap();
+        assert!(!pkg.is_part_unmodified(pkg.get_part(&image).unwrap()));
+    }
+
+    #[test]
+    fn test_save_rewrites_mostly_modified_package() {
+        let zip_data = create_docx_with_media();
+        let mut pkg = OpcPackage::from_reader(Cursor::new(zip_data)).unwrap();
+        let partnames: Vec<PackURI> = pkg.iter_parts().map(|p| p.partname().clone()).collect();
+        for partname in &partnames {
+            let part = pkg.get_part_mut(partname).unwrap();
+            let blob = part.blob().to_vec();
+            part.set_blob(blob);
+        }
+
+        let saved = crate::ooxml::opc::pkgwriter::PackageWriter::to_bytes(&pkg).unwrap();
+        let reopened = OpcPackage::from_bytes(&saved).unwrap();
+        assert_eq!(reopened.part_count(), partnames.len());
+        assert!(reopened.source_archive().is_none());
+        for partname in &partnames {
+            assert_eq!(
+                reopened.get_part(partname).unwrap().blob(),
+                pkg.get_part(partname).unwrap().blob()
+            );
+        }
+    }
 }
@@ -1,16 +1,19 @@
+use crate::common::trace;
 use crate::ooxml::opc::error::{OpcError, Result};
 use crate::ooxml::opc::packuri::PackURI;
 use crate::ooxml::opc::rel::Relationships;
 use memchr::memmem;
 use quick_xml::Reader;
 use quick_xml::events::Event;
+use soapberry_zip::office::FileArchive;
 /// Open Packaging Convention (OPC) objects related to package parts.
 ///
 /// This module provides the Part trait and XmlPart implementation for representing
 /// parts within an OPC package. Parts are the fundamental units of content in an
 /// OPC package, each with a unique partname, content type, and optional relationships.
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::io::Read;
+use std::sync::{Arc, OnceLock};
 
 /// Trait representing a part in an OPC package.
 ///
@@ -32,11 +35,45 @@ pub trait Part: Send + Sync {
     /// This allows creating sub-slices that share the same allocation.
     fn blob_arc(&self) -> Arc<Vec<u8>>;
 
+    /// Get the binary content of this part, failing if it cannot be read.
+    ///
+    /// Only deferred parts can fail, when their package file can no longer
+    /// be read; [`blob`](Self::blob) returns empty content for them then.
+    fn try_blob(&self) -> Result<&[u8]> {
+        Ok(self.blob())
+    }
+
+    /// Get the binary content as a shared Arc, failing if it cannot be read.
+    ///
+    /// See [`try_blob`](Self::try_blob).
+    fn try_blob_arc(&self) -> Result<Arc<Vec<u8>>> {
+        Ok(self.blob_arc())
+    }
+
     /// Set the binary content of this part.
     ///
     /// This allows for modification of part content.
     fn set_blob(&mut self, blob: Vec<u8>);
 
+    /// Whether the content of this part has not been read from the package
+    /// file yet.
+    ///
+    /// Large parts of packages opened from a path are deferred: their content
+    /// is read on the first call to [`blob`](Self::blob), while
+    /// [`content_reader`](Self::content_reader) streams it without keeping it.
+    fn is_deferred(&self) -> bool {
+        false
+    }
+
+    /// Get a reader over the binary content of this part.
+    ///
+    /// Deferred parts are decompressed from the package file as the reader
+    /// is consumed, so this is preferable to [`blob`](Self::blob) for parts
+    /// that are parsed once.
+    fn content_reader(&self) -> Result<Box<dyn Read + Send + '_>> {
+        Ok(Box::new(self.blob()))
+    }
+
     /// Get the relationships for this part.
     fn rels(&self) -> &Relationships;
 
@@ -260,7 +297,14 @@ impl XmlPart {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(OpcError::XmlError(format!("XML parse error: {}", e))),
+                Err(e) => {
+                    return Err(OpcError::malformed_xml(
+                        self.partname.as_str(),
+                        &self.xml_bytes,
+                        reader.error_position(),
+                        e,
+                    ));
+                },
                 _ => {},
             }
         }
@@ -295,7 +339,14 @@ impl XmlPart {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(OpcError::XmlError(format!("XML parse error: {}", e))),
+                Err(e) => {
+                    return Err(OpcError::malformed_xml(
+                        self.partname.as_str(),
+                        &self.xml_bytes,
+                        reader.error_position(),
+                        e,
+                    ));
+                },
                 _ => {},
             }
         }
@@ -343,6 +394,114 @@ impl Part for XmlPart {
     }
 }
 
+/// A part whose content stays in the package file until it is needed.
+///
+/// Packages opened from a path use this for large parts, so that opening
+/// them does not decompress the whole part. See [`Part::is_deferred`].
+#[derive(Debug)]
+pub(crate) struct DeferredPart {
+    /// The partname (URI) of this part
+    partname: PackURI,
+
+    /// The content type of this part
+    content_type: String,
+
+    /// The package archive the content is read from
+    source: Arc<FileArchive>,
+
+    /// The content, once it has been read or replaced
+    blob: OnceLock<Arc<Vec<u8>>>,
+
+    /// Relationships from this part to other parts
+    rels: Relationships,
+}
+
+impl DeferredPart {
+    /// Create a part whose content is read from `source` on demand.
+    pub(crate) fn new(partname: PackURI, content_type: String, source: Arc<FileArchive>) -> Self {
+        let rels = Relationships::new(partname.base_uri().to_string());
+        Self {
+            partname,
+            content_type,
+            source,
+            blob: OnceLock::new(),
+            rels,
+        }
+    }
+
+    /// Get the content, reading it from the package file on first use.
+    ///
+    /// If the file can no longer be read, the part stays deferred.
+    fn load(&self) -> Result<&Arc<Vec<u8>>> {
+        if let Some(blob) = self.blob.get() {
+            return Ok(blob);
+        }
+        let data = self.source.read(self.partname.membername())?;
+        Ok(self.blob.get_or_init(|| Arc::new(data)))
+    }
+
+    /// Get the content for the infallible accessors, which log read errors
+    /// and return empty content instead.
+    fn loaded(&self) -> Option<&Arc<Vec<u8>>> {
+        self.load()
+            .inspect_err(|_err| {
+                trace::warn!(part = %self.partname, error = %_err, "failed to read deferred part");
+            })
+            .ok()
+    }
+}
+
+impl Part for DeferredPart {
+    fn partname(&self) -> &PackURI {
+        &self.partname
+    }
+
+    fn content_type(&self) -> &str {
+        &self.content_type
+    }
+
+    fn blob(&self) -> &[u8] {
+        self.loaded().map_or(&[], |blob| blob.as_slice())
+    }
+
+    fn blob_arc(&self) -> Arc<Vec<u8>> {
+        self.loaded().cloned().unwrap_or_default()
+    }
+
+    fn try_blob(&self) -> Result<&[u8]> {
+        self.load().map(|blob| blob.as_slice())
+    }
+
+    fn try_blob_arc(&self) -> Result<Arc<Vec<u8>>> {
+        self.load().cloned()
+    }
+
+    fn set_blob(&mut self, blob: Vec<u8>) {
+        self.blob = OnceLock::from(Arc::new(blob));
+    }
+
+    fn is_deferred(&self) -> bool {
+        self.blob.get().is_none()
+    }
+
+    fn content_reader(&self) -> Result<Box<dyn Read + Send + '_>> {
+        match self.blob.get() {
+            Some(blob) => Ok(Box::new(blob.as_slice())),
+            None => Ok(Box::new(
+                self.source.read_stream(self.partname.membername())?,
+            )),
+        }
+    }
+
+    fn rels(&self) -> &Relationships {
+        &self.rels
+    }
+
+    fn rels_mut(&mut self) -> &mut Relationships {
+        &mut self.rels
+    }
+}
+
 /// Factory for creating Part instances based on content type.
 ///
 /// The factory uses a type-based dispatch system to create the appropriate
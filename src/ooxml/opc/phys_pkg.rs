@@ -7,7 +7,8 @@
 
 use crate::ooxml::opc::error::{OpcError, Result};
 use crate::ooxml::opc::packuri::PackURI;
-use soapberry_zip::office::LazyArchiveReader;
+use soapberry_zip::office::{FileArchive, LazyArchiveReader};
+use soapberry_zip::{FileReader, ReaderAt};
 use std::io::Read;
 use std::path::Path;
 
@@ -146,6 +147,15 @@ impl<'data> PhysPkgReader<'data> {
         Ok(Self { archive })
     }
 
+    /// Create a PhysPkgReader from a possibly damaged ZIP archive.
+    ///
+    /// Falls back to scanning local file headers when the central directory
+    /// cannot be read, see [`LazyArchiveReader::recover`].
+    pub fn recover(data: &'data [u8]) -> Result<Self> {
+        let archive = LazyArchiveReader::recover(data)?;
+        Ok(Self { archive })
+    }
+
     /// Get the binary content for a part by its PackURI.
     ///
     /// Uses efficient lazy decompression. The returned vector contains
@@ -270,7 +280,7 @@ impl PhysPkgWriter {
     pub fn write(&mut self, pack_uri: &PackURI, blob: &[u8]) -> Result<()> {
         self.archive
             .write_deflated(pack_uri.membername(), blob)
-            .map_err(|e| OpcError::ZipError(e.to_string()))
+            .map_err(OpcError::ZipError)
     }
 
     /// Write a part to the package without compression (stored).
@@ -281,16 +291,14 @@ impl PhysPkgWriter {
     pub fn write_stored(&mut self, pack_uri: &PackURI, blob: &[u8]) -> Result<()> {
         self.archive
             .write_stored(pack_uri.membername(), blob)
-            .map_err(|e| OpcError::ZipError(e.to_string()))
+            .map_err(OpcError::ZipError)
     }
 
     /// Finish writing and return the package bytes.
     ///
     /// Consumes the writer and returns the complete ZIP archive.
     pub fn finish(self) -> Result<Vec<u8>> {
-        self.archive
-            .finish_to_bytes()
-            .map_err(|e| OpcError::ZipError(e.to_string()))
+        self.archive.finish_to_bytes().map_err(OpcError::ZipError)
     }
 }
 
@@ -300,6 +308,75 @@ impl Default for PhysPkgWriter {
     }
 }
 
+/// Physical package editor for updating an existing OPC package.
+///
+/// Members that are not written or removed are copied from the source
+/// archive byte-for-byte, so unmodified parts are never recompressed.
+pub struct PhysPkgEditor<R> {
+    /// The underlying ZIP archive editor
+    editor: soapberry_zip::office::ArchiveEditor<R>,
+}
+
+impl<'data> PhysPkgEditor<std::io::Cursor<&'data [u8]>> {
+    /// Create a new editor over the given package archive.
+    ///
+    /// # Arguments
+    /// * `data` - The ZIP archive data of the source package
+    pub fn new(data: &'data [u8]) -> Result<Self> {
+        let editor =
+            soapberry_zip::office::ArchiveEditor::from_slice(data).map_err(OpcError::ZipError)?;
+        Ok(Self { editor })
+    }
+}
+
+impl<'a> PhysPkgEditor<&'a FileReader> {
+    /// Create a new editor over a package archive that is read from its file.
+    pub fn from_file_archive(archive: &'a FileArchive) -> Result<Self> {
+        let editor = archive.editor().map_err(OpcError::ZipError)?;
+        Ok(Self { editor })
+    }
+}
+
+impl<R: ReaderAt> PhysPkgEditor<R> {
+    /// List the member names of the source package.
+    pub fn source_member_names(&self) -> Vec<String> {
+        self.editor.source_names().map(str::to_string).collect()
+    }
+
+    /// Check if the edited package will contain a member for the PackURI.
+    pub fn contains(&self, pack_uri: &PackURI) -> bool {
+        self.editor.contains(pack_uri.membername())
+    }
+
+    /// Check if the source package has a member for the PackURI that can be
+    /// copied as is.
+    ///
+    /// Members of a package read from its file must still have the CRC32
+    /// and size they had when the package was opened.
+    pub fn is_source_unchanged(&self, pack_uri: &PackURI) -> bool {
+        self.editor.is_source_unchanged(pack_uri.membername())
+    }
+
+    /// Replace or add a part with Deflate compression.
+    ///
+    /// # Arguments
+    /// * `pack_uri` - The PackURI for the part
+    /// * `blob` - The binary content to write
+    pub fn write(&mut self, pack_uri: &PackURI, blob: &[u8]) {
+        self.editor.write_deflated(pack_uri.membername(), blob);
+    }
+
+    /// Remove a member by its name within the archive.
+    pub fn remove(&mut self, membername: &str) {
+        self.editor.remove(membername);
+    }
+
+    /// Finish editing and return the package bytes.
+    pub fn finish(self) -> Result<Vec<u8>> {
+        self.editor.finish_to_bytes().map_err(OpcError::ZipError)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -4,6 +4,8 @@
 //! content type mapping, relationship resolution, and part loading. It uses
 //! efficient algorithms for parsing and minimal memory allocation.
 
+use crate::common::trace;
+use crate::ooxml::opc::constants::content_type as ct;
 use crate::ooxml::opc::constants::target_mode;
 use crate::ooxml::opc::error::{OpcError, Result};
 use crate::ooxml::opc::packuri::{PACKAGE_URI, PackURI};
@@ -11,8 +13,72 @@ use crate::ooxml::opc::phys_pkg::PhysPkgReader;
 use quick_xml::Reader;
 use quick_xml::events::Event;
 use smallvec::SmallVec;
+use soapberry_zip::office::{FileArchive, LazyArchiveReader};
 use std::collections::HashMap;
 
+/// ZIP archive a package is read from, either held in memory or read from
+/// its file on demand.
+trait PackageArchive: Sync {
+    /// Whether the entries were recovered from local file headers.
+    fn is_recovered(&self) -> bool;
+
+    /// Names of all files in the archive.
+    fn file_names(&self) -> impl Iterator<Item = &str>;
+
+    /// Decompressed size of a file as recorded in the archive.
+    fn uncompressed_size(&self, name: &str) -> Option<u64>;
+
+    /// Read and decompress a file.
+    fn read(&self, name: &str) -> std::result::Result<Vec<u8>, soapberry_zip::Error>;
+
+    /// Read and decompress files in parallel, leaving out unreadable ones.
+    fn read_many_parallel(&self, names: &[&str]) -> HashMap<String, Vec<u8>>;
+}
+
+impl PackageArchive for LazyArchiveReader<'_> {
+    fn is_recovered(&self) -> bool {
+        LazyArchiveReader::is_recovered(self)
+    }
+
+    fn file_names(&self) -> impl Iterator<Item = &str> {
+        LazyArchiveReader::file_names(self)
+    }
+
+    fn uncompressed_size(&self, name: &str) -> Option<u64> {
+        LazyArchiveReader::uncompressed_size(self, name)
+    }
+
+    fn read(&self, name: &str) -> std::result::Result<Vec<u8>, soapberry_zip::Error> {
+        LazyArchiveReader::read(self, name)
+    }
+
+    fn read_many_parallel(&self, names: &[&str]) -> HashMap<String, Vec<u8>> {
+        LazyArchiveReader::read_many_parallel(self, names)
+    }
+}
+
+impl PackageArchive for FileArchive {
+    fn is_recovered(&self) -> bool {
+        false
+    }
+
+    fn file_names(&self) -> impl Iterator<Item = &str> {
+        FileArchive::file_names(self)
+    }
+
+    fn uncompressed_size(&self, name: &str) -> Option<u64> {
+        FileArchive::uncompressed_size(self, name)
+    }
+
+    fn read(&self, name: &str) -> std::result::Result<Vec<u8>, soapberry_zip::Error> {
+        FileArchive::read(self, name)
+    }
+
+    fn read_many_parallel(&self, names: &[&str]) -> HashMap<String, Vec<u8>> {
+        FileArchive::read_many_parallel(self, names)
+    }
+}
+
 /// Serialized part with its content and relationships.
 ///
 /// Represents a part as loaded from the physical package, before
@@ -31,6 +97,10 @@ pub struct SerializedPart {
     /// The binary content of this part
     pub blob: Vec<u8>,
 
+    /// Whether the content was left in the archive because the part is
+    /// large, in which case `blob` is empty
+    pub deferred: bool,
+
     /// Serialized relationships from this part
     /// Uses SmallVec for efficient storage of typically small relationship collections
     pub srels: SmallVec<[SerializedRelationship; 8]>,
@@ -83,7 +153,7 @@ impl SerializedRelationship {
 ///
 /// Implements the OPC content type discovery algorithm using Default and Override elements
 /// from [Content_Types].xml. Uses efficient hash maps for O(1) lookup.
-struct ContentTypeMap {
+pub(crate) struct ContentTypeMap {
     /// Maps file extensions to default content types
     defaults: HashMap<String, String>,
 
@@ -103,7 +173,7 @@ impl ContentTypeMap {
     /// Parse content types from [Content_Types].xml.
     ///
     /// Uses quick-xml for efficient streaming XML parsing with minimal allocation.
-    fn from_xml(xml: &[u8]) -> Result<Self> {
+    pub(crate) fn from_xml(xml: &[u8]) -> Result<Self> {
         let mut map = Self::new();
         let mut reader = Reader::from_reader(xml);
         reader.config_mut().trim_text(true);
@@ -173,10 +243,12 @@ impl ContentTypeMap {
                 },
                 Ok(Event::Eof) => break,
                 Err(e) => {
-                    return Err(OpcError::XmlError(format!(
-                        "Content types parse error: {}",
-                        e
-                    )));
+                    return Err(OpcError::malformed_xml(
+                        crate::ooxml::opc::packuri::CONTENT_TYPES_URI,
+                        xml,
+                        reader.error_position(),
+                        e,
+                    ));
                 },
                 _ => {},
             }
@@ -199,7 +271,7 @@ impl ContentTypeMap {
     ///
     /// First checks for an override, then falls back to the default
     /// based on file extension.
-    fn get(&self, pack_uri: &PackURI) -> Result<String> {
+    pub(crate) fn get(&self, pack_uri: &PackURI) -> Result<String> {
         // Check override first
         if let Some(ct) = self.overrides.get(pack_uri.as_str()) {
             return Ok(ct.clone());
@@ -213,6 +285,11 @@ impl ContentTypeMap {
 
         Err(OpcError::ContentTypeNotFound(pack_uri.to_string()))
     }
+
+    /// Partnames that have an override content type.
+    pub(crate) fn override_partnames(&self) -> impl Iterator<Item = &str> {
+        self.overrides.keys().map(String::as_str)
+    }
 }
 
 /// Package reader that provides access to serialized parts and relationships.
@@ -226,6 +303,9 @@ pub struct PackageReader {
 
     /// All serialized parts in the package
     sparts: Vec<SerializedPart>,
+
+    /// Whether the package was damaged and read in recovery mode
+    recovered: bool,
 }
 
 impl PackageReader {
@@ -238,7 +318,7 @@ impl PackageReader {
     ///
     /// This approach is faster than pre-loading everything because:
     /// - Parsing can start while other files are still being decompressed
-    /// - Files not in the relationship graph are never decompressed
+    /// - Relationship files are small, so the part graph is known before any large part is read
     /// - Memory pressure is reduced (don't hold all decompressed data at once)
     ///
     /// # Arguments
@@ -247,15 +327,51 @@ impl PackageReader {
     /// # Returns
     /// A new PackageReader with all parts and relationships loaded
     pub fn from_phys_reader(phys_reader: &PhysPkgReader<'_>) -> Result<Self> {
-        let archive = phys_reader.archive();
+        Self::read(phys_reader.archive(), false, None)
+    }
+
+    /// Parse an OPC package read from its file, leaving the content of parts
+    /// of at least `min_size` bytes in the archive.
+    ///
+    /// Such parts are returned with [`SerializedPart::deferred`] set so that
+    /// they can be read from the package file when they are needed.
+    pub(crate) fn from_file_archive(archive: &FileArchive, min_size: u64) -> Result<Self> {
+        Self::read(archive, false, Some(min_size))
+    }
+
+    /// Parse a possibly damaged OPC package.
+    ///
+    /// Unlike [`from_phys_reader`](Self::from_phys_reader), a missing or
+    /// malformed `[Content_Types].xml` is tolerated: content types are inferred
+    /// from part extensions and the relationships that point at each part.
+    /// Parts that cannot be read are dropped. Use
+    /// [`is_recovered`](Self::is_recovered) to find out whether anything had
+    /// to be repaired.
+    pub fn recover(phys_reader: &PhysPkgReader<'_>) -> Result<Self> {
+        Self::read(phys_reader.archive(), true, None)
+    }
+
+    fn read(
+        archive: &impl PackageArchive,
+        recovery: bool,
+        defer_from: Option<u64>,
+    ) -> Result<Self> {
+        let mut recovered = archive.is_recovered();
 
         // Phase 1: Decompress and parse content types (on-demand)
-        let content_types_path =
-            crate::ooxml::opc::packuri::CONTENT_TYPES_URI.trim_start_matches('/');
-        let content_types_xml = archive
-            .read(content_types_path)
-            .map_err(|_| OpcError::PartNotFound("[Content_Types].xml".to_string()))?;
-        let content_types = ContentTypeMap::from_xml(&content_types_xml)?;
+        let content_types = archive
+            .read(content_types_membername())
+            .map_err(|_| OpcError::PartNotFound("[Content_Types].xml".to_string()))
+            .and_then(|xml| ContentTypeMap::from_xml(&xml));
+        let content_types = match content_types {
+            Ok(content_types) => content_types,
+            Err(_err) if recovery => {
+                trace::warn!(error = %_err, "inferring content types from part names");
+                recovered = true;
+                ContentTypeMap::new()
+            },
+            Err(err) => return Err(err),
+        };
 
         // Phase 2: Get package-level relationships (on-demand decompression)
         let package_uri = PackURI::new(PACKAGE_URI).map_err(OpcError::InvalidPackUri)?;
@@ -263,14 +379,33 @@ impl PackageReader {
 
         // Phase 3: Load all parts by walking the relationship graph
         // Each part is decompressed on-demand as it's traversed
-        let sparts = Self::load_parts_lazy(archive, &pkg_srels, &content_types)?;
+        let sparts = Self::load_parts_lazy(
+            archive,
+            &pkg_srels,
+            &content_types,
+            recovery.then_some(&mut recovered),
+            defer_from,
+        )?;
+
+        Ok(Self {
+            pkg_srels,
+            sparts,
+            recovered,
+        })
+    }
 
-        Ok(Self { pkg_srels, sparts })
+    /// Whether the package was damaged and had to be repaired while reading.
+    ///
+    /// Always false for packages read with
+    /// [`from_phys_reader`](Self::from_phys_reader).
+    pub fn is_recovered(&self) -> bool {
+        self.recovered
     }
 
     /// Parse relationships XML into SerializedRelationship structs.
     fn parse_rels_xml(
         rels_xml: &[u8],
+        rels_uri: &PackURI,
         base_uri: &str,
     ) -> Result<SmallVec<[SerializedRelationship; 8]>> {
         let mut srels = SmallVec::new();
@@ -328,7 +463,14 @@ impl PackageReader {
                     }
                 },
                 Ok(Event::Eof) => break,
-                Err(e) => return Err(OpcError::XmlError(format!("Rels parse error: {}", e))),
+                Err(e) => {
+                    return Err(OpcError::malformed_xml(
+                        rels_uri.as_str(),
+                        rels_xml,
+                        reader.error_position(),
+                        e,
+                    ));
+                },
                 _ => {},
             }
         }
@@ -339,33 +481,50 @@ impl PackageReader {
     /// Load relationships using lazy on-demand decompression.
     ///
     /// Decompresses and parses the relationships file for a given source URI.
-    /// The result is cached by the lazy archive reader for subsequent access.
+    /// In-memory archives cache the result for subsequent access.
     fn load_rels_lazy(
-        archive: &soapberry_zip::office::LazyArchiveReader<'_>,
+        archive: &impl PackageArchive,
         source_uri: &PackURI,
     ) -> Result<SmallVec<[SerializedRelationship; 8]>> {
         let rels_uri = source_uri.rels_uri().map_err(OpcError::InvalidPackUri)?;
         let rels_path = rels_uri.membername();
 
         match archive.read(rels_path) {
-            Ok(rels_xml) => Self::parse_rels_xml(&rels_xml, source_uri.base_uri()),
+            Ok(rels_xml) => Self::parse_rels_xml(&rels_xml, &rels_uri, source_uri.base_uri()),
             Err(_) => Ok(SmallVec::new()), // No relationships file
         }
     }
 
-    /// Load all parts using parallel decompression of only referenced files.
+    /// Load all parts using parallel decompression.
     ///
     /// This is a two-phase approach for maximum performance:
-    /// 1. First pass: traverse relationship graph using small .rels files to discover all parts
+    /// 1. First pass: traverse relationship graph using small .rels files to discover all parts,
+    ///    then add any remaining members that have a content type
     /// 2. Second pass: decompress ALL discovered part contents in PARALLEL
     ///
-    /// This avoids decompressing unreferenced files (like unused images) while still
-    /// getting full parallel decompression for all needed parts.
+    /// Parts nobody refers to are kept as well, so that saving the package
+    /// writes back everything it was opened with.
+    ///
+    /// In recovery mode, `recovered` is given and gets set when a content
+    /// type had to be inferred or a part could not be read.
     fn load_parts_lazy(
-        archive: &soapberry_zip::office::LazyArchiveReader<'_>,
+        archive: &impl PackageArchive,
         pkg_srels: &[SerializedRelationship],
         content_types: &ContentTypeMap,
+        mut recovered: Option<&mut bool>,
+        defer_from: Option<u64>,
     ) -> Result<Vec<SerializedPart>> {
+        let recovery = recovered.is_some();
+        // The content type of a part, and whether it had to be inferred
+        let content_type_of = |partname: &PackURI, reltype: &str| match content_types.get(partname)
+        {
+            Ok(content_type) => Ok((content_type, false)),
+            Err(err) if recovery => infer_content_type(partname, reltype)
+                .map(|content_type| (content_type.to_string(), true))
+                .ok_or(err),
+            Err(err) => Err(err),
+        };
+
         use std::collections::HashSet;
 
         // Phase 1: Discover all parts by traversing relationships (small files only)
@@ -409,31 +568,77 @@ impl PackageReader {
             discovered.push((partname, reltype, part_srels));
         }
 
+        // Keep members that no relationship points at (e.g. leftovers from
+        // third-party tools) so they survive a round-trip. Only members with a
+        // declared content type are valid parts.
+        let orphans: Vec<PackURI> = archive
+            .file_names()
+            .filter(|name| {
+                !visited.contains(&format!("/{name}"))
+                    && *name != content_types_membername()
+                    && !is_rels_membername(name)
+            })
+            .filter_map(|name| PackURI::new(format!("/{name}")).ok())
+            .filter(|partname| content_type_of(partname, "").is_ok())
+            .collect();
+        for partname in orphans {
+            let part_srels = Self::load_rels_lazy(archive, &partname)?;
+            discovered.push((partname, String::new(), part_srels));
+        }
+
+        // Parts at or above the deferral size stay compressed in the archive
+        let is_deferred = |partname: &PackURI| {
+            defer_from.is_some_and(|min_size| {
+                archive
+                    .uncompressed_size(partname.membername())
+                    .is_some_and(|size| size >= min_size)
+            })
+        };
+
         // Phase 2: Parallel decompression of all discovered parts
         // Collect member names for parallel batch read
         let member_names: Vec<&str> = discovered
             .iter()
+            .filter(|(partname, _, _)| !is_deferred(partname))
             .map(|(partname, _, _)| partname.membername())
             .collect();
 
         // Decompress all part contents in parallel
-        let mut decompressed = archive.read_many_parallel(&member_names);
+        let mut decompressed = {
+            let _span = trace::debug_span!("read_parts", parts = member_names.len()).entered();
+            archive.read_many_parallel(&member_names)
+        };
 
         // Phase 3: Build SerializedPart structures (take ownership, no cloning)
         let mut sparts = Vec::with_capacity(discovered.len());
         for (partname, reltype, part_srels) in discovered {
             let membername = partname.membername();
+            let deferred = is_deferred(&partname);
             // Remove from map to take ownership instead of cloning
-            let blob = decompressed
-                .remove(membername)
-                .ok_or_else(|| OpcError::PartNotFound(partname.to_string()))?;
-            let content_type = content_types.get(&partname)?;
+            let blob = if deferred {
+                Some(Vec::new())
+            } else {
+                decompressed.remove(membername)
+            };
+            let Some(blob) = blob else {
+                if let Some(recovered) = recovered.as_deref_mut() {
+                    trace::warn!(part = %partname, "dropping part that could not be read");
+                    *recovered = true;
+                    continue;
+                }
+                return Err(OpcError::PartNotFound(partname.to_string()));
+            };
+            let (content_type, inferred) = content_type_of(&partname, &reltype)?;
+            if inferred && let Some(recovered) = recovered.as_deref_mut() {
+                *recovered = true;
+            }
 
             sparts.push(SerializedPart {
                 partname,
                 content_type,
                 reltype,
                 blob,
+                deferred,
                 srels: part_srels,
             });
         }
@@ -462,6 +667,85 @@ impl PackageReader {
     }
 }
 
+/// The membername of the [Content_Types].xml item.
+#[inline]
+fn content_types_membername() -> &'static str {
+    crate::ooxml::opc::packuri::CONTENT_TYPES_URI.trim_start_matches('/')
+}
+
+/// Guess the content type of a part the package does not declare one for.
+///
+/// Media and other binary parts are recognized by their extension. XML parts
+/// get the content type that goes with the relationship pointing at them,
+/// which for shared relationship types such as `styles` depends on whether
+/// the part belongs to a Word, Excel or PowerPoint package.
+fn infer_content_type(partname: &PackURI, reltype: &str) -> Option<&'static str> {
+    match partname.ext().to_ascii_lowercase().as_str() {
+        "png" => return Some(ct::PNG),
+        "jpeg" | "jpg" => return Some(ct::JPEG),
+        "gif" => return Some(ct::GIF),
+        "bmp" => return Some(ct::BMP),
+        "tif" | "tiff" => return Some(ct::TIFF),
+        "emf" => return Some(ct::X_EMF),
+        "wmf" => return Some(ct::X_WMF),
+        "bin" => return Some(ct::OFC_OLE_OBJECT),
+        "vml" => return Some(ct::OFC_VML_DRAWING),
+        "xml" => {},
+        _ => return None,
+    }
+
+    let kind = reltype.rsplit('/').next().unwrap_or_default();
+    let directory = partname.membername().split('/').next().unwrap_or_default();
+    let content_type = match (directory, kind) {
+        (_, "core-properties") => ct::OPC_CORE_PROPERTIES,
+        (_, "extended-properties") => ct::OFC_EXTENDED_PROPERTIES,
+        (_, "custom-properties") => ct::OFC_CUSTOM_PROPERTIES,
+        (_, "theme") => ct::OFC_THEME,
+        (_, "chart") => ct::DML_CHART,
+        ("word", "officeDocument") => ct::WML_DOCUMENT_MAIN,
+        ("word", "styles") => ct::WML_STYLES,
+        ("word", "settings") => ct::WML_SETTINGS,
+        ("word", "webSettings") => ct::WML_WEB_SETTINGS,
+        ("word", "fontTable") => ct::WML_FONT_TABLE,
+        ("word", "numbering") => ct::WML_NUMBERING,
+        ("word", "footnotes") => ct::WML_FOOTNOTES,
+        ("word", "endnotes") => ct::WML_ENDNOTES,
+        ("word", "comments") => ct::WML_COMMENTS,
+        ("word", "header") => ct::WML_HEADER,
+        ("word", "footer") => ct::WML_FOOTER,
+        ("xl", "officeDocument") => ct::SML_SHEET_MAIN,
+        ("xl", "worksheet") => ct::SML_WORKSHEET,
+        ("xl", "styles") => ct::SML_STYLES,
+        ("xl", "sharedStrings") => ct::SML_SHARED_STRINGS,
+        ("xl", "comments") => ct::SML_COMMENTS,
+        ("xl", "table") => ct::SML_TABLE,
+        ("xl", "drawing") => ct::OFC_DRAWING,
+        ("xl", "pivotTable") => ct::SML_PIVOT_TABLE,
+        ("xl", "pivotCacheDefinition") => ct::SML_PIVOT_CACHE_DEFINITION,
+        ("xl", "pivotCacheRecords") => ct::SML_PIVOT_CACHE_RECORDS,
+        ("ppt", "officeDocument") => ct::PML_PRESENTATION_MAIN,
+        ("ppt", "slide") => ct::PML_SLIDE,
+        ("ppt", "slideLayout") => ct::PML_SLIDE_LAYOUT,
+        ("ppt", "slideMaster") => ct::PML_SLIDE_MASTER,
+        ("ppt", "notesSlide") => ct::PML_NOTES_SLIDE,
+        ("ppt", "notesMaster") => ct::PML_NOTES_MASTER,
+        ("ppt", "handoutMaster") => ct::PML_HANDOUT_MASTER,
+        ("ppt", "presProps") => ct::PML_PRES_PROPS,
+        ("ppt", "viewProps") => ct::PML_VIEW_PROPS,
+        ("ppt", "tableStyles") => ct::PML_TABLE_STYLES,
+        ("ppt", "comments") => ct::PML_COMMENTS,
+        ("ppt", "commentAuthors") => ct::PML_COMMENT_AUTHORS,
+        _ => ct::XML,
+    };
+    Some(content_type)
+}
+
+/// Check whether a membername is a relationships item (`.../_rels/*.rels`).
+#[inline]
+fn is_rels_membername(name: &str) -> bool {
+    name.ends_with(".rels") && (name.starts_with("_rels/") || name.contains("/_rels/"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -486,4 +770,26 @@ mod tests {
             "application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"
         );
     }
+
+    #[test]
+    fn test_infer_content_type() {
+        use crate::ooxml::opc::constants::relationship_type as rt;
+
+        let infer = |partname: &str, reltype: &str| {
+            infer_content_type(&PackURI::new(partname).unwrap(), reltype)
+        };
+
+        assert_eq!(
+            infer("/word/document.xml", rt::OFFICE_DOCUMENT),
+            Some(ct::WML_DOCUMENT_MAIN)
+        );
+        assert_eq!(
+            infer("/xl/workbook.xml", rt::OFFICE_DOCUMENT),
+            Some(ct::SML_SHEET_MAIN)
+        );
+        assert_eq!(infer("/word/styles.xml", rt::STYLES), Some(ct::WML_STYLES));
+        assert_eq!(infer("/word/media/image1.PNG", ""), Some(ct::PNG));
+        assert_eq!(infer("/customXml/item1.xml", ""), Some(ct::XML));
+        assert_eq!(infer("/word/unknown.dat", ""), None);
+    }
 }
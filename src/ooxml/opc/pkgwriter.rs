@@ -2,13 +2,16 @@
 //!
 //! This module provides functionality to serialize and write OPC packages to disk,
 //! including writing the [Content_Types].xml, relationships, and all parts.
+use crate::common::trace;
 use crate::common::xml::escape_xml;
 use crate::ooxml::opc::constants::content_type as ct;
 use crate::ooxml::opc::error::Result;
 use crate::ooxml::opc::package::OpcPackage;
 use crate::ooxml::opc::packuri::{CONTENT_TYPES_URI, PACKAGE_URI, PackURI};
-use crate::ooxml::opc::phys_pkg::PhysPkgWriter;
-use std::collections::HashMap;
+use crate::ooxml::opc::phys_pkg::{PhysPkgEditor, PhysPkgWriter};
+use crate::ooxml::opc::rel::Relationships;
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
 use std::path::Path;
 
 /// Package writer that serializes an OPC package to a ZIP file.
@@ -62,26 +65,113 @@ impl PackageWriter {
     /// # Returns
     /// The serialized package as a byte vector
     pub fn to_bytes(package: &OpcPackage) -> Result<Vec<u8>> {
+        // Signature parts are left out entirely when stripping signatures
+        let mut skipped = package.signature_partnames();
+        if !package.save_options().strip_signatures {
+            if !skipped.is_empty()
+                && package
+                    .iter_parts()
+                    .any(|part| !package.is_part_unmodified(part))
+            {
+                trace::warn!("saving a modified signed package, its signatures will not validate");
+            }
+            skipped.clear();
+            if let Some(bytes) = Self::to_bytes_incremental(package)? {
+                return Ok(bytes);
+            }
+        }
+
         let mut phys_writer = PhysPkgWriter::new();
 
         // Write [Content_Types].xml
-        Self::write_content_types(&mut phys_writer, package)?;
+        Self::write_content_types(&mut phys_writer, package, &skipped)?;
 
         // Write package-level relationships (_rels/.rels)
-        Self::write_pkg_rels(&mut phys_writer, package)?;
+        Self::write_pkg_rels(&mut phys_writer, package, &skipped)?;
 
         // Write all parts and their relationships
-        Self::write_parts(&mut phys_writer, package)?;
+        Self::write_parts(&mut phys_writer, package, &skipped)?;
 
         // Finish writing and return the bytes
         phys_writer.finish()
     }
 
+    /// Serialize a package by editing the archive it was opened from.
+    ///
+    /// Unmodified parts are copied from the source archive without being
+    /// recompressed, while content types and relationships are always
+    /// regenerated. Parts whose member in the source file changed since the
+    /// package was opened are written from memory instead; for deferred parts
+    /// this fails, as their content was never loaded. Returns `None` when the package has no source archive,
+    /// when the source file can no longer be read as an archive (for example
+    /// because it was truncated to be overwritten), or when fewer than half
+    /// of its parts are unmodified, since a full rewrite costs about the same
+    /// then.
+    fn to_bytes_incremental(package: &OpcPackage) -> Result<Option<Vec<u8>>> {
+        let Some(source) = package.source_archive() else {
+            return Ok(None);
+        };
+        let unmodified = package
+            .iter_parts()
+            .filter(|part| package.is_part_unmodified(*part))
+            .count();
+        if unmodified * 2 <= package.part_count() {
+            return Ok(None);
+        }
+
+        let Ok(mut editor) = PhysPkgEditor::from_file_archive(source) else {
+            trace::warn!("source package can no longer be read, rewriting all parts");
+            return Ok(None);
+        };
+        let mut members = HashSet::with_capacity(package.part_count() * 2 + 2);
+
+        let content_types_uri = PackURI::new(CONTENT_TYPES_URI)
+            .map_err(crate::ooxml::opc::error::OpcError::InvalidPackUri)?;
+        let content_types = ContentTypesItem::from_package(package, &HashSet::new()).to_xml();
+        editor.write(&content_types_uri, content_types.as_bytes());
+        members.insert(content_types_uri.membername().to_string());
+
+        let pkg_rels_uri = PackURI::new(PACKAGE_URI)
+            .and_then(|uri| uri.rels_uri())
+            .map_err(crate::ooxml::opc::error::OpcError::InvalidPackUri)?;
+        editor.write(&pkg_rels_uri, package.rels().to_xml().as_bytes());
+        members.insert(pkg_rels_uri.membername().to_string());
+
+        for part in package.iter_parts() {
+            let partname = part.partname();
+            if !package.is_part_unmodified(part) || !editor.is_source_unchanged(partname) {
+                editor.write(partname, part.try_blob()?);
+            }
+            members.insert(partname.membername().to_string());
+
+            if !part.rels().is_empty() {
+                let rels_uri = partname
+                    .rels_uri()
+                    .map_err(crate::ooxml::opc::error::OpcError::InvalidPackUri)?;
+                editor.write(&rels_uri, part.rels().to_xml().as_bytes());
+                members.insert(rels_uri.membername().to_string());
+            }
+        }
+
+        // Drop members whose parts or relationships no longer exist
+        for name in editor.source_member_names() {
+            if !members.contains(&name) {
+                editor.remove(&name);
+            }
+        }
+
+        editor.finish().map(Some)
+    }
+
     /// Write the [Content_Types].xml part.
     ///
     /// This file maps file extensions and part names to content types.
-    fn write_content_types(phys_writer: &mut PhysPkgWriter, package: &OpcPackage) -> Result<()> {
-        let cti = ContentTypesItem::from_package(package);
+    fn write_content_types(
+        phys_writer: &mut PhysPkgWriter,
+        package: &OpcPackage,
+        skipped: &HashSet<PackURI>,
+    ) -> Result<()> {
+        let cti = ContentTypesItem::from_package(package, skipped);
         let blob = cti.to_xml();
 
         let content_types_uri = PackURI::new(CONTENT_TYPES_URI)
@@ -91,25 +181,63 @@ impl PackageWriter {
         Ok(())
     }
 
-    /// Write package-level relationships.
-    fn write_pkg_rels(phys_writer: &mut PhysPkgWriter, package: &OpcPackage) -> Result<()> {
+    /// Write package-level relationships, leaving out those that target
+    /// skipped parts.
+    fn write_pkg_rels(
+        phys_writer: &mut PhysPkgWriter,
+        package: &OpcPackage,
+        skipped: &HashSet<PackURI>,
+    ) -> Result<()> {
         let package_uri = PackURI::new(PACKAGE_URI)
             .map_err(crate::ooxml::opc::error::OpcError::InvalidPackUri)?;
         let rels_uri = package_uri
             .rels_uri()
             .map_err(crate::ooxml::opc::error::OpcError::InvalidPackUri)?;
-        let rels_xml = package.rels().to_xml();
+        let rels_xml = if skipped.is_empty() {
+            package.rels().to_xml()
+        } else {
+            let mut rels = Relationships::new(PACKAGE_URI.to_string());
+            for rel in package.rels().iter() {
+                if rel
+                    .target_partname()
+                    .is_ok_and(|partname| skipped.contains(&partname))
+                {
+                    continue;
+                }
+                rels.add_relationship(
+                    rel.reltype().to_string(),
+                    rel.target_ref().to_string(),
+                    rel.r_id().to_string(),
+                    rel.is_external(),
+                );
+            }
+            rels.to_xml()
+        };
         phys_writer.write(&rels_uri, rels_xml.as_bytes())?;
 
         Ok(())
     }
 
     /// Write all parts and their relationships.
-    fn write_parts(phys_writer: &mut PhysPkgWriter, package: &OpcPackage) -> Result<()> {
-        for part in package.iter_parts() {
-            // Write the part itself
-            let blob = part.blob();
-            phys_writer.write(part.partname(), blob)?;
+    fn write_parts(
+        phys_writer: &mut PhysPkgWriter,
+        package: &OpcPackage,
+        skipped: &HashSet<PackURI>,
+    ) -> Result<()> {
+        for part in package
+            .iter_parts()
+            .filter(|part| !skipped.contains(part.partname()))
+        {
+            // Write the part itself. Deferred parts are read through their
+            // reader so that a source file that can no longer be read is an
+            // error rather than an empty part.
+            if part.is_deferred() {
+                let mut blob = Vec::new();
+                part.content_reader()?.read_to_end(&mut blob)?;
+                phys_writer.write(part.partname(), &blob)?;
+            } else {
+                phys_writer.write(part.partname(), part.blob())?;
+            }
 
             // Write the part's relationships if it has any
             if !part.rels().is_empty() {
@@ -152,11 +280,14 @@ impl ContentTypesItem {
         }
     }
 
-    /// Build ContentTypesItem from an OPC package.
-    fn from_package(package: &OpcPackage) -> Self {
+    /// Build ContentTypesItem from an OPC package, leaving out skipped parts.
+    fn from_package(package: &OpcPackage, skipped: &HashSet<PackURI>) -> Self {
         let mut cti = Self::new();
 
-        for part in package.iter_parts() {
+        for part in package
+            .iter_parts()
+            .filter(|part| !skipped.contains(part.partname()))
+        {
             cti.add_content_type(part.partname(), part.content_type());
         }
 
@@ -0,0 +1,781 @@
+//! Structural validation of serialized OPC packages.
+//!
+//! Office applications open a damaged package only after asking to repair
+//! it, and they rarely say what was wrong. The checks here catch the usual
+//! causes in generated files: parts without a content type, relationships
+//! pointing at parts that do not exist, duplicate relationship ids, missing
+//! main parts and XML that is not well-formed.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use litchi::ooxml::opc::{OpcPackage, validation};
+//!
+//! let package = OpcPackage::open("generated.xlsx")?;
+//! for issue in validation::validate(&package)? {
+//!     println!("{issue}");
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::ooxml::opc::constants::{content_type as ct, relationship_type as rt, target_mode};
+use crate::ooxml::opc::error::Result;
+use crate::ooxml::opc::package::OpcPackage;
+use crate::ooxml::opc::packuri::{CONTENT_TYPES_URI, PACKAGE_URI, PackURI};
+use crate::ooxml::opc::pkgreader::ContentTypeMap;
+use crate::ooxml::opc::pkgwriter::PackageWriter;
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use soapberry_zip::office::ArchiveReader;
+use std::collections::HashSet;
+use std::fmt;
+
+/// How serious a validation issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Tolerated by Office applications, but likely a mistake
+    Warning,
+    /// Makes Office applications reject or repair the package
+    Error,
+}
+
+/// What a validation issue is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum IssueKind {
+    /// `[Content_Types].xml` is missing or cannot be parsed
+    ContentTypes,
+    /// A part has neither a default nor an override content type
+    MissingContentType,
+    /// An override content type names a part that does not exist
+    UnusedOverride,
+    /// A relationship lacks its id, type or target
+    InvalidRelationship,
+    /// Two relationships of the same source share an id
+    DuplicateRelationshipId,
+    /// An internal relationship targets a part that does not exist
+    BrokenRelationship,
+    /// A relationships part belongs to a part that does not exist
+    OrphanRelationships,
+    /// A part required for the document type does not exist
+    MissingRequiredPart,
+    /// An XML part is not well-formed
+    MalformedXml,
+    /// A part cannot be decompressed
+    UnreadablePart,
+}
+
+/// A problem found in a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationIssue {
+    /// How serious the issue is
+    pub severity: Severity,
+    /// What the issue is about
+    pub kind: IssueKind,
+    /// Partname of the offending part, `/` for the package itself
+    pub part: String,
+    /// Id of the offending relationship, if any
+    pub relationship: Option<String>,
+    /// Human-readable description
+    pub message: String,
+}
+
+impl ValidationIssue {
+    /// Whether Office applications would reject or repair the package.
+    #[inline]
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}", self.part)?;
+        if let Some(r_id) = &self.relationship {
+            write!(f, " ({r_id})")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Validate a package as it would be saved.
+///
+/// The package is serialized first, so the checks see exactly what an Office
+/// application would open.
+pub fn validate(package: &OpcPackage) -> Result<Vec<ValidationIssue>> {
+    validate_bytes(&PackageWriter::to_bytes(package)?)
+}
+
+/// Validate a serialized package.
+///
+/// Fails only when `data` is not a ZIP archive; everything else wrong with
+/// the package is reported as a [`ValidationIssue`].
+pub fn validate_bytes(data: &[u8]) -> Result<Vec<ValidationIssue>> {
+    let archive = ArchiveReader::new(data)?;
+    let mut validator = Validator::new(&archive);
+    validator.run();
+    Ok(validator.issues)
+}
+
+/// A relationship as written in a `.rels` part.
+struct RawRelationship {
+    r_id: Option<String>,
+    reltype: Option<String>,
+    target: Option<String>,
+    external: bool,
+}
+
+struct Validator<'a> {
+    archive: &'a ArchiveReader<'a>,
+    /// Membernames in a stable order, without directories
+    members: Vec<String>,
+    /// Lowercased partnames, since partnames compare case-insensitively
+    partnames: HashSet<String>,
+    issues: Vec<ValidationIssue>,
+}
+
+impl<'a> Validator<'a> {
+    fn new(archive: &'a ArchiveReader<'a>) -> Self {
+        let mut members: Vec<String> = archive
+            .file_names()
+            .filter(|name| !name.ends_with('/'))
+            .map(str::to_string)
+            .collect();
+        members.sort();
+        let partnames = members
+            .iter()
+            .map(|name| format!("/{}", name.to_lowercase()))
+            .collect();
+        Self {
+            archive,
+            members,
+            partnames,
+            issues: Vec::new(),
+        }
+    }
+
+    fn report(
+        &mut self,
+        severity: Severity,
+        kind: IssueKind,
+        part: &str,
+        relationship: Option<&str>,
+        message: impl Into<String>,
+    ) {
+        self.issues.push(ValidationIssue {
+            severity,
+            kind,
+            part: part.to_string(),
+            relationship: relationship.map(str::to_string),
+            message: message.into(),
+        });
+    }
+
+    fn has_part(&self, partname: &str) -> bool {
+        self.partnames.contains(&partname.to_lowercase())
+    }
+
+    fn run(&mut self) {
+        let content_types = self.check_content_types();
+
+        for member in self.members.clone() {
+            let partname = format!("/{member}");
+            if partname == CONTENT_TYPES_URI {
+                continue;
+            }
+            let Ok(pack_uri) = PackURI::new(partname.as_str()) else {
+                continue;
+            };
+            let content_type = content_types
+                .as_ref()
+                .and_then(|map| map.get(&pack_uri).ok());
+            if content_types.is_some() && content_type.is_none() {
+                self.report(
+                    Severity::Error,
+                    IssueKind::MissingContentType,
+                    &partname,
+                    None,
+                    "part has no content type",
+                );
+            }
+
+            let is_rels = is_rels_membername(&member);
+            if !is_rels && !content_type.as_deref().is_some_and(is_xml_content_type) {
+                continue;
+            }
+            let Some(xml) = self.read(&partname) else {
+                continue;
+            };
+            if let Err(message) = check_well_formed(&xml) {
+                self.report(
+                    Severity::Error,
+                    IssueKind::MalformedXml,
+                    &partname,
+                    None,
+                    message,
+                );
+                continue;
+            }
+            if is_rels {
+                self.check_relationships(&member, &xml);
+            }
+        }
+
+        self.check_required_parts(content_types.as_ref());
+    }
+
+    /// Check `[Content_Types].xml` and return its mappings.
+    fn check_content_types(&mut self) -> Option<ContentTypeMap> {
+        if !self.archive.contains(&CONTENT_TYPES_URI[1..]) {
+            self.report(
+                Severity::Error,
+                IssueKind::ContentTypes,
+                CONTENT_TYPES_URI,
+                None,
+                "package has no [Content_Types].xml",
+            );
+            return None;
+        }
+        let xml = self.read(CONTENT_TYPES_URI)?;
+        let map = match check_well_formed(&xml)
+            .and_then(|()| ContentTypeMap::from_xml(&xml).map_err(|err| err.to_string()))
+        {
+            Ok(map) => map,
+            Err(message) => {
+                self.report(
+                    Severity::Error,
+                    IssueKind::ContentTypes,
+                    CONTENT_TYPES_URI,
+                    None,
+                    message,
+                );
+                return None;
+            },
+        };
+
+        let mut unused: Vec<String> = map
+            .override_partnames()
+            .filter(|partname| !self.has_part(partname))
+            .map(str::to_string)
+            .collect();
+        unused.sort();
+        for partname in unused {
+            self.report(
+                Severity::Warning,
+                IssueKind::UnusedOverride,
+                CONTENT_TYPES_URI,
+                None,
+                format!("override for missing part {partname}"),
+            );
+        }
+        Some(map)
+    }
+
+    /// Read a part, reporting it when it cannot be read.
+    fn read(&mut self, partname: &str) -> Option<Vec<u8>> {
+        match self.archive.read(&partname[1..]) {
+            Ok(data) => Some(data),
+            Err(err) => {
+                self.report(
+                    Severity::Error,
+                    IssueKind::UnreadablePart,
+                    partname,
+                    None,
+                    err.to_string(),
+                );
+                None
+            },
+        }
+    }
+
+    /// Check the relationships in the `.rels` part `member`.
+    fn check_relationships(&mut self, member: &str, xml: &[u8]) {
+        let rels_partname = format!("/{member}");
+        let Some(source) = rels_source(member) else {
+            return;
+        };
+        if source != PACKAGE_URI && !self.has_part(&source) {
+            self.report(
+                Severity::Warning,
+                IssueKind::OrphanRelationships,
+                &rels_partname,
+                None,
+                format!("source part {source} does not exist"),
+            );
+        }
+        let base_uri = PackURI::new(source.as_str())
+            .map(|uri| uri.base_uri().to_string())
+            .unwrap_or_else(|_| PACKAGE_URI.to_string());
+
+        let relationships = match parse_relationships(xml) {
+            Ok(relationships) => relationships,
+            Err(message) => {
+                self.report(
+                    Severity::Error,
+                    IssueKind::MalformedXml,
+                    &rels_partname,
+                    None,
+                    message,
+                );
+                return;
+            },
+        };
+
+        let mut ids = HashSet::new();
+        for rel in relationships {
+            let (Some(r_id), Some(_), Some(target)) = (&rel.r_id, &rel.reltype, &rel.target) else {
+                self.report(
+                    Severity::Error,
+                    IssueKind::InvalidRelationship,
+                    &rels_partname,
+                    rel.r_id.as_deref(),
+                    "relationship needs an Id, a Type and a Target",
+                );
+                continue;
+            };
+            if !ids.insert(r_id.clone()) {
+                self.report(
+                    Severity::Error,
+                    IssueKind::DuplicateRelationshipId,
+                    &rels_partname,
+                    Some(r_id),
+                    "relationship id is used more than once",
+                );
+            }
+            if rel.external {
+                continue;
+            }
+            // Fragments point inside the target part
+            let target_path = target.split('#').next().unwrap_or_default();
+            if target_path.is_empty() {
+                continue;
+            }
+            let resolved = PackURI::from_rel_ref(&base_uri, target_path);
+            if !resolved.is_ok_and(|uri| self.has_part(uri.as_str())) {
+                self.report(
+                    Severity::Error,
+                    IssueKind::BrokenRelationship,
+                    &rels_partname,
+                    Some(r_id),
+                    format!("target {target} does not exist"),
+                );
+            }
+        }
+    }
+
+    /// Check that the main part and the parts it cannot do without exist.
+    fn check_required_parts(&mut self, content_types: Option<&ContentTypeMap>) {
+        let Some(main) = self.related_parts(PACKAGE_URI, rt::OFFICE_DOCUMENT).next() else {
+            self.report(
+                Severity::Error,
+                IssueKind::MissingRequiredPart,
+                PACKAGE_URI,
+                None,
+                "package has no officeDocument relationship",
+            );
+            return;
+        };
+        if !self.has_part(&main) {
+            // Already reported as a broken relationship
+            return;
+        }
+
+        let content_type = content_types
+            .zip(PackURI::new(main.as_str()).ok())
+            .and_then(|(map, uri)| map.get(&uri).ok())
+            .unwrap_or_default();
+        let (reltypes, description): (&[&str], _) = if is_spreadsheet_main(&content_type) {
+            (&[rt::WORKSHEET, rt::CHARTSHEET], "a sheet")
+        } else if is_presentation_main(&content_type) {
+            (&[rt::SLIDE_MASTER], "a slide master")
+        } else {
+            return;
+        };
+
+        let found = reltypes.iter().any(|reltype| {
+            self.related_parts(&main, reltype)
+                .any(|partname| self.has_part(&partname))
+        });
+        if !found {
+            self.report(
+                Severity::Error,
+                IssueKind::MissingRequiredPart,
+                &main,
+                None,
+                format!("main part has no relationship to {description}"),
+            );
+        }
+    }
+
+    /// Partnames targeted by the internal relationships of type `reltype`
+    /// from `source`.
+    fn related_parts(&self, source: &str, reltype: &str) -> impl Iterator<Item = String> {
+        let base_uri = PackURI::new(source)
+            .map(|uri| uri.base_uri().to_string())
+            .unwrap_or_else(|_| PACKAGE_URI.to_string());
+        let relationships = PackURI::new(source)
+            .and_then(|uri| uri.rels_uri())
+            .ok()
+            .and_then(|rels_uri| self.archive.read(rels_uri.membername()).ok())
+            .and_then(|xml| parse_relationships(&xml).ok())
+            .unwrap_or_default();
+
+        relationships.into_iter().filter_map(move |rel| {
+            if rel.external
+                || !rel
+                    .reltype
+                    .as_deref()
+                    .is_some_and(|t| is_reltype(t, reltype))
+            {
+                return None;
+            }
+            let target = rel.target?;
+            PackURI::from_rel_ref(&base_uri, &target)
+                .ok()
+                .map(|uri| uri.as_str().to_string())
+        })
+    }
+}
+
+/// Check that `xml` is a single, properly nested XML element tree.
+fn check_well_formed(xml: &[u8]) -> std::result::Result<(), String> {
+    let mut reader = Reader::from_reader(xml);
+    let mut depth = 0usize;
+    let mut roots = 0usize;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(_)) => {
+                if depth == 0 {
+                    roots += 1;
+                }
+                depth += 1;
+            },
+            Ok(Event::End(_)) => depth = depth.saturating_sub(1),
+            Ok(Event::Empty(_)) if depth == 0 => roots += 1,
+            Ok(Event::Eof) => break,
+            Ok(_) => {},
+            Err(err) => {
+                let line = crate::common::error::types::line_at(xml, reader.error_position());
+                return Err(format!("line {line}: {err}"));
+            },
+        }
+    }
+
+    match (roots, depth) {
+        (0, _) => Err("no root element".to_string()),
+        (_, 0) if roots > 1 => Err("more than one root element".to_string()),
+        (_, 0) => Ok(()),
+        _ => Err("document ends inside an element".to_string()),
+    }
+}
+
+/// Parse every `Relationship` element of a `.rels` part, complete or not.
+fn parse_relationships(xml: &[u8]) -> std::result::Result<Vec<RawRelationship>, String> {
+    let mut relationships = Vec::new();
+    let mut reader = Reader::from_reader(xml);
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Empty(ref e)) | Ok(Event::Start(ref e))
+                if e.local_name().as_ref() == b"Relationship" =>
+            {
+                let mut rel = RawRelationship {
+                    r_id: None,
+                    reltype: None,
+                    target: None,
+                    external: false,
+                };
+                for attr in e.attributes() {
+                    let attr = attr.map_err(|err| err.to_string())?;
+                    let value = attr
+                        .decode_and_unescape_value(reader.decoder())
+                        .map_err(|err| err.to_string())?
+                        .into_owned();
+                    match attr.key.as_ref() {
+                        b"Id" => rel.r_id = Some(value),
+                        b"Type" => rel.reltype = Some(value),
+                        b"Target" => rel.target = Some(value),
+                        b"TargetMode" => rel.external = value == target_mode::EXTERNAL,
+                        _ => {},
+                    }
+                }
+                relationships.push(rel);
+            },
+            Ok(Event::Eof) => break,
+            Ok(_) => {},
+            Err(err) => return Err(err.to_string()),
+        }
+    }
+
+    Ok(relationships)
+}
+
+/// The partname of the source of the `.rels` member `member`.
+fn rels_source(member: &str) -> Option<String> {
+    let stem = member.strip_suffix(".rels")?;
+    if let Some(filename) = stem.strip_prefix("_rels/") {
+        return Some(format!("/{filename}"));
+    }
+    let (directory, filename) = stem.rsplit_once("/_rels/")?;
+    Some(format!("/{directory}/{filename}"))
+}
+
+/// Whether `reltype` is `expected` or its Strict Open XML equivalent.
+fn is_reltype(reltype: &str, expected: &str) -> bool {
+    const TRANSITIONAL: &str =
+        "http://schemas.openxmlformats.org/officeDocument/2006/relationships/";
+    const STRICT: &str = "http://purl.oclc.org/ooxml/officeDocument/relationships/";
+
+    reltype == expected
+        || expected
+            .strip_prefix(TRANSITIONAL)
+            .zip(reltype.strip_prefix(STRICT))
+            .is_some_and(|(expected, reltype)| expected == reltype)
+}
+
+#[inline]
+fn is_rels_membername(name: &str) -> bool {
+    name.ends_with(".rels") && (name.starts_with("_rels/") || name.contains("/_rels/"))
+}
+
+#[inline]
+fn is_xml_content_type(content_type: &str) -> bool {
+    content_type.ends_with("+xml") || content_type == ct::XML || content_type == "text/xml"
+}
+
+fn is_spreadsheet_main(content_type: &str) -> bool {
+    content_type == ct::SML_SHEET_MAIN
+        || content_type == ct::XLSB_BIN
+        || content_type.starts_with("application/vnd.ms-excel.sheet.")
+        || content_type.starts_with("application/vnd.ms-excel.template.")
+        || content_type.ends_with("spreadsheetml.template.main+xml")
+}
+
+fn is_presentation_main(content_type: &str) -> bool {
+    content_type == ct::PML_PRESENTATION_MAIN
+        || content_type == ct::PML_PRES_MACRO_MAIN
+        || content_type.starts_with("application/vnd.ms-powerpoint.")
+            && content_type.ends_with(".main+xml")
+        || content_type.ends_with("presentationml.template.main+xml")
+        || content_type.ends_with("presentationml.slideshow.main+xml")
+}
+
+/// Panic with every issue found unless `data` is a package without any.
+#[cfg(test)]
+pub(crate) fn assert_valid_package(data: &[u8]) {
+    let issues = validate_bytes(data).unwrap();
+    let report: Vec<String> = issues.iter().map(ToString::to_string).collect();
+    assert!(issues.is_empty(), "invalid package:\n{}", report.join("\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soapberry_zip::office::StreamingArchiveWriter;
+
+    const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+</Types>"#;
+
+    const PACKAGE_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/></Relationships>"#;
+
+    const DOCUMENT: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body/></w:document>"#;
+
+    /// A minimal .docx with `members` added to, or replacing, its parts.
+    fn docx(members: &[(&str, &str)]) -> Vec<u8> {
+        let base = [
+            ("[Content_Types].xml", CONTENT_TYPES),
+            ("_rels/.rels", PACKAGE_RELS),
+            ("word/document.xml", DOCUMENT),
+        ];
+        let mut writer = StreamingArchiveWriter::new();
+        for (name, data) in base {
+            if !members.iter().any(|(member, _)| *member == name) {
+                writer.write_deflated(name, data.as_bytes()).unwrap();
+            }
+        }
+        for (name, data) in members {
+            writer.write_deflated(name, data.as_bytes()).unwrap();
+        }
+        writer.finish_to_bytes().unwrap()
+    }
+
+    fn document_rels(relationships: &str) -> String {
+        format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{relationships}</Relationships>"#
+        )
+    }
+
+    fn kinds(issues: &[ValidationIssue]) -> Vec<IssueKind> {
+        issues.iter().map(|issue| issue.kind).collect()
+    }
+
+    #[test]
+    fn test_minimal_package_is_valid() {
+        assert_valid_package(&docx(&[]));
+    }
+
+    #[test]
+    fn test_broken_and_duplicate_relationships() {
+        let rels = document_rels(concat!(
+            r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>"#,
+            r#"<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/hyperlink" Target="https://example.com" TargetMode="External"/>"#,
+            r#"<Relationship Id="rId2" Target="settings.xml"/>"#,
+        ));
+        let issues = validate_bytes(&docx(&[("word/_rels/document.xml.rels", &rels)])).unwrap();
+
+        assert_eq!(
+            kinds(&issues),
+            [
+                IssueKind::BrokenRelationship,
+                IssueKind::DuplicateRelationshipId,
+                IssueKind::InvalidRelationship,
+            ]
+        );
+        assert!(issues.iter().all(ValidationIssue::is_error));
+        assert_eq!(issues[0].part, "/word/_rels/document.xml.rels");
+        assert_eq!(issues[0].relationship.as_deref(), Some("rId1"));
+        assert_eq!(
+            issues[0].to_string(),
+            "error: /word/_rels/document.xml.rels (rId1): target styles.xml does not exist"
+        );
+    }
+
+    #[test]
+    fn test_missing_content_type() {
+        let issues = validate_bytes(&docx(&[("word/media/image1.png", "png")])).unwrap();
+        assert_eq!(kinds(&issues), [IssueKind::MissingContentType]);
+        assert_eq!(issues[0].part, "/word/media/image1.png");
+    }
+
+    #[test]
+    fn test_missing_content_types_part() {
+        let mut writer = StreamingArchiveWriter::new();
+        writer
+            .write_deflated("_rels/.rels", PACKAGE_RELS.as_bytes())
+            .unwrap();
+        writer
+            .write_deflated("word/document.xml", DOCUMENT.as_bytes())
+            .unwrap();
+        let issues = validate_bytes(&writer.finish_to_bytes().unwrap()).unwrap();
+        assert_eq!(kinds(&issues), [IssueKind::ContentTypes]);
+    }
+
+    #[test]
+    fn test_malformed_xml_reports_line() {
+        let document = "<w:document xmlns:w=\"urn:w\">\n<w:body></w:p>\n</w:document>";
+        let issues = validate_bytes(&docx(&[("word/document.xml", document)])).unwrap();
+        assert_eq!(kinds(&issues), [IssueKind::MalformedXml]);
+        assert!(issues[0].message.starts_with("line 2:"), "{}", issues[0]);
+
+        let unclosed = "<w:document xmlns:w=\"urn:w\"><w:body>";
+        let issues = validate_bytes(&docx(&[("word/document.xml", unclosed)])).unwrap();
+        assert_eq!(kinds(&issues), [IssueKind::MalformedXml]);
+    }
+
+    #[test]
+    fn test_unused_override_and_orphan_relationships() {
+        let content_types = CONTENT_TYPES.replace(
+            "</Types>",
+            r#"<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/></Types>"#,
+        );
+        let issues = validate_bytes(&docx(&[
+            ("[Content_Types].xml", &content_types),
+            ("word/_rels/gone.xml.rels", &document_rels("")),
+        ]))
+        .unwrap();
+        assert_eq!(
+            kinds(&issues),
+            [IssueKind::UnusedOverride, IssueKind::OrphanRelationships]
+        );
+        assert!(issues.iter().all(|issue| !issue.is_error()));
+    }
+
+    #[test]
+    fn test_required_parts() {
+        let issues = validate_bytes(&docx(&[("_rels/.rels", &document_rels(""))])).unwrap();
+        assert_eq!(kinds(&issues), [IssueKind::MissingRequiredPart]);
+        assert_eq!(issues[0].part, "/");
+
+        // A workbook without any sheet
+        let content_types = CONTENT_TYPES.replace(
+            "wordprocessingml.document.main+xml",
+            "spreadsheetml.sheet.main+xml",
+        );
+        let issues = validate_bytes(&docx(&[("[Content_Types].xml", &content_types)])).unwrap();
+        assert_eq!(kinds(&issues), [IssueKind::MissingRequiredPart]);
+        assert_eq!(issues[0].part, "/word/document.xml");
+    }
+
+    #[test]
+    fn test_writer_output_is_valid() {
+        use std::io::Cursor;
+
+        let mut docx = crate::ooxml::docx::Package::new().unwrap();
+        let document = docx.document_mut().unwrap();
+        document.add_heading("Title", 1).unwrap();
+        document.add_paragraph_with_text("Body");
+        document.add_table(2, 2);
+        let mut bytes = Cursor::new(Vec::new());
+        docx.to_stream(&mut bytes).unwrap();
+        assert_valid_package(bytes.get_ref());
+
+        let mut workbook = crate::ooxml::xlsx::Workbook::create().unwrap();
+        workbook
+            .worksheet_mut(0)
+            .unwrap()
+            .set_cell_value(1, 1, "Name");
+        workbook.add_worksheet("Summary").set_cell_value(1, 1, 42);
+        let mut bytes = Cursor::new(Vec::new());
+        workbook.to_stream(&mut bytes).unwrap();
+        assert_valid_package(bytes.get_ref());
+
+        let mut pptx = crate::ooxml::pptx::Package::new().unwrap();
+        let slide = pptx.presentation_mut().unwrap().add_slide().unwrap();
+        slide.set_title("Welcome");
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("validate.pptx");
+        pptx.save(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_valid_package(&bytes);
+    }
+
+    #[test]
+    fn test_round_trip_adds_no_errors() {
+        let errors = |issues: Vec<ValidationIssue>| -> HashSet<_> {
+            issues
+                .into_iter()
+                .filter(ValidationIssue::is_error)
+                .map(|issue| (issue.kind, issue.part, issue.relationship))
+                .collect()
+        };
+
+        for dir in [
+            "test-data/ooxml/docx",
+            "test-data/ooxml/xlsx",
+            "test-data/ooxml/pptx",
+        ] {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                let data = std::fs::read(&path).unwrap();
+                let Ok(package) = OpcPackage::from_bytes(&data) else {
+                    continue;
+                };
+                let before = errors(validate_bytes(&data).unwrap());
+                let after = errors(validate(&package).unwrap());
+                let added: Vec<_> = after.difference(&before).collect();
+                assert!(added.is_empty(), "{}: {added:?}", path.display());
+            }
+        }
+    }
+}
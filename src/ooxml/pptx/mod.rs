@@ -67,6 +67,8 @@ pub mod package;
 pub mod parts;
 pub mod presentation;
 pub mod protection;
+#[cfg(feature = "render")]
+pub mod render;
 pub mod sections;
 pub mod shapes;
 pub mod slide;
@@ -95,4 +97,4 @@ pub use sections::{Section, SectionList};
 pub use slide::{Slide, SlideLayout, SlideMaster};
 pub use smartart::{DiagramNode, DiagramType, SmartArt, SmartArtBuilder};
 pub use transitions::{SlideTransition, TransitionDirection, TransitionSpeed, TransitionType};
-pub use writer::{MutablePresentation, MutableShape, MutableSlide};
+pub use writer::{MutablePresentation, MutableShape, MutableSlide, MutableTable, MutableTableCell};
@@ -1,5 +1,8 @@
 /// Package implementation for PowerPoint presentations.
+use crate::common::Metadata;
 use crate::ooxml::common::DocumentProperties;
+use crate::ooxml::common::properties::write_property_part;
+use crate::ooxml::custom_properties::CustomProperties;
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::OpcPackage;
 use crate::ooxml::opc::constants::content_type as ct;
@@ -53,6 +56,8 @@ pub struct Package {
     mutable_pres: Option<MutablePresentation>,
     /// Document properties (metadata)
     properties: DocumentProperties,
+    /// Custom document properties
+    custom_properties: CustomProperties,
 }
 
 #[cfg(feature = "fonts")]
@@ -92,7 +97,7 @@ impl EmbedFonts for Package {
 
         // Update presentation.xml content with embedded font references
         if let Ok(pres_part) = self.opc.get_part_mut(&pres_uri) {
-            let xml_content = std::str::from_utf8(pres_part.blob())
+            let xml_content = std::str::from_utf8(pres_part.try_blob()?)
                 .map_err(|e| OoxmlError::Other(format!("Invalid presentation.xml: {}", e)))?;
 
             let mut updated_xml = xml_content.to_string();
@@ -351,6 +356,7 @@ impl Package {
             opc,
             mutable_pres,
             properties,
+            custom_properties: CustomProperties::new(),
         })
     }
 
@@ -389,10 +395,15 @@ impl Package {
             });
         }
 
+        let properties = DocumentProperties::from_package(&opc);
+        let custom_properties = crate::ooxml::custom_properties::extract_custom_properties(&opc)
+            .unwrap_or_else(|_| CustomProperties::new());
+
         Ok(Self {
             opc,
             mutable_pres: None,
-            properties: DocumentProperties::new(),
+            properties,
+            custom_properties,
         })
     }
 
@@ -443,10 +454,15 @@ impl Package {
             });
         }
 
+        let properties = DocumentProperties::from_package(&opc);
+        let custom_properties = crate::ooxml::custom_properties::extract_custom_properties(&opc)
+            .unwrap_or_else(|_| CustomProperties::new());
+
         Ok(Self {
             opc,
             mutable_pres: None,
-            properties: DocumentProperties::new(),
+            properties,
+            custom_properties,
         })
     }
 
@@ -488,10 +504,15 @@ impl Package {
             });
         }
 
+        let properties = DocumentProperties::from_package(&opc);
+        let custom_properties = crate::ooxml::custom_properties::extract_custom_properties(&opc)
+            .unwrap_or_else(|_| CustomProperties::new());
+
         Ok(Self {
             opc,
             mutable_pres: None,
-            properties: DocumentProperties::new(),
+            properties,
+            custom_properties,
         })
     }
 
@@ -605,6 +626,39 @@ impl Package {
         &mut self.properties
     }
 
+    /// Get a reference to the custom presentation properties.
+    pub fn custom_properties(&self) -> &CustomProperties {
+        &self.custom_properties
+    }
+
+    /// Get a mutable reference to the custom presentation properties.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::custom_properties::PropertyValue;
+    /// use litchi::ooxml::pptx::Package;
+    ///
+    /// let mut pkg = Package::new()?;
+    /// pkg.custom_properties_mut()
+    ///     .add_property("Reviewed", PropertyValue::Boolean(true));
+    /// pkg.save("presentation.pptx")?;
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn custom_properties_mut(&mut self) -> &mut CustomProperties {
+        &mut self.custom_properties
+    }
+
+    /// Replace the presentation properties and custom properties with `metadata`.
+    ///
+    /// Core fields go to docProps/core.xml, application/company/manager/template
+    /// to docProps/app.xml and the custom property map to docProps/custom.xml
+    /// when the package is saved.
+    pub fn set_metadata(&mut self, metadata: &Metadata) {
+        self.properties = DocumentProperties::from(metadata);
+        self.custom_properties = CustomProperties::from(&metadata.custom_properties);
+    }
+
     /// Save the package to a file.
     ///
     /// Writes the complete PowerPoint presentation including all parts, relationships,
@@ -639,8 +693,10 @@ impl Package {
             }
         }
 
-        // Update core properties
+        // Update document properties
         self.update_core_properties()?;
+        self.update_app_properties()?;
+        self.update_custom_properties()?;
 
         // Embed fonts if feature enabled and requested in options
         #[cfg(feature = "fonts")]
@@ -1303,24 +1359,53 @@ impl Package {
 
     /// Update the core.xml properties part.
     fn update_core_properties(&mut self) -> Result<()> {
-        use crate::ooxml::opc::part::BlobPart;
-
-        let core_uri = PackURI::new("/docProps/core.xml")
-            .map_err(|e| OoxmlError::InvalidUri(format!("core.xml URI: {}", e)))?;
+        use crate::ooxml::opc::constants::relationship_type as rt;
 
-        // Generate XML from properties
+        self.properties.stamp();
         let xml = self.properties.to_xml();
+        write_property_part(
+            &mut self.opc,
+            rt::CORE_PROPERTIES,
+            "/docProps/core.xml",
+            ct::OPC_CORE_PROPERTIES,
+            |_| Ok(xml),
+        )
+    }
 
-        // Create or update the core properties part
-        let core_part = BlobPart::new(
-            core_uri,
-            ct::OPC_CORE_PROPERTIES.to_string(),
-            xml.into_bytes(),
-        );
+    /// Merge the extended properties into the app.xml part.
+    fn update_app_properties(&mut self) -> Result<()> {
+        use crate::ooxml::opc::constants::relationship_type as rt;
 
-        self.opc.add_part(Box::new(core_part));
+        let properties = &self.properties;
+        write_property_part(
+            &mut self.opc,
+            rt::EXTENDED_PROPERTIES,
+            "/docProps/app.xml",
+            ct::OFC_EXTENDED_PROPERTIES,
+            |existing| match existing.and_then(|xml| std::str::from_utf8(xml).ok()) {
+                Some(xml) => properties.merge_app_xml(xml),
+                None => properties.to_app_xml(),
+            },
+        )
+    }
 
-        Ok(())
+    /// Update the custom.xml properties part.
+    fn update_custom_properties(&mut self) -> Result<()> {
+        use crate::ooxml::opc::constants::relationship_type as rt;
+
+        // Only create custom properties part if there are custom properties
+        if self.custom_properties.is_empty() {
+            return Ok(());
+        }
+
+        let xml = self.custom_properties.to_xml()?;
+        write_property_part(
+            &mut self.opc,
+            rt::CUSTOM_PROPERTIES,
+            "/docProps/custom.xml",
+            ct::OFC_CUSTOM_PROPERTIES,
+            |_| Ok(xml),
+        )
     }
 }
 
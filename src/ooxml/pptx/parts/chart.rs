@@ -59,6 +59,7 @@ pub struct ChartPart<'a> {
 impl<'a> ChartPart<'a> {
     /// Create a ChartPart from an OPC Part.
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        part.try_blob()?;
         Ok(Self { part })
     }
 
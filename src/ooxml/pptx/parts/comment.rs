@@ -178,6 +178,7 @@ pub struct CommentsPart<'a> {
 impl<'a> CommentsPart<'a> {
     /// Create a CommentsPart from an OPC Part.
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        part.try_blob()?;
         Ok(Self { part })
     }
 
@@ -327,6 +328,7 @@ pub struct CommentAuthorsPart<'a> {
 impl<'a> CommentAuthorsPart<'a> {
     /// Create a CommentAuthorsPart from an OPC Part.
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        part.try_blob()?;
         Ok(Self { part })
     }
 
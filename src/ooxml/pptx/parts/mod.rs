@@ -17,5 +17,5 @@ pub use comment::{
     generate_comments_xml,
 };
 pub use presentation::PresentationPart;
-pub use slide::{SlideLayoutPart, SlideMasterPart, SlidePart};
+pub use slide::{DiagramText, SlideLayoutPart, SlideMasterPart, SlidePart};
 pub use theme::{Theme, ThemeColor, ThemeFont, ThemePart};
@@ -35,6 +35,7 @@ impl<'a> PresentationPart<'a> {
     /// let pres_part = PresentationPart::from_part(opc_part)?;
     /// ```
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        part.try_blob()?;
         Ok(Self { part })
     }
 
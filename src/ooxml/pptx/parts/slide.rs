@@ -1,11 +1,26 @@
 /// Slide parts and related types.
 ///
 /// This module contains parts for slides, slide layouts, and slide masters.
+use crate::common::trace;
+use crate::common::walk::{TextEvent, visit, visit_lines};
+use crate::ooxml::docx::content_control::push_general_ref;
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::part::Part;
 use crate::ooxml::pptx::shapes::base::{BaseShape, ShapeType};
+use crate::ooxml::pptx::smartart::data_rel_id;
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
+use std::ops::ControlFlow;
+
+/// Text left out when extracting slide text.
+#[derive(Debug, Clone, Copy, Default)]
+struct SkipText {
+    tables: bool,
+    titles: bool,
+}
+
+/// Resolves the text of a SmartArt diagram from its data part relationship ID.
+pub type DiagramText<'f> = &'f dyn Fn(&str) -> Result<Option<String>>;
 
 /// A slide part.
 ///
@@ -18,6 +33,9 @@ pub struct SlidePart<'a> {
 impl<'a> SlidePart<'a> {
     /// Create a SlidePart from an OPC Part.
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        // Read deferred content now, so that a package file that can no
+        // longer be read fails here instead of yielding empty XML
+        part.try_blob()?;
         Ok(Self { part })
     }
 
@@ -60,21 +78,97 @@ impl<'a> SlidePart<'a> {
     ///
     /// This extracts text from all `<a:t>` elements in the slide (DrawingML text).
     pub fn extract_text(&self) -> Result<String> {
+        self.extract_text_filtered(SkipText::default(), &|_| Ok(None))
+    }
+
+    /// Extract all text content from the slide, including SmartArt text.
+    ///
+    /// `diagram_text` maps the data part relationship ID (`r:dm`) of each
+    /// SmartArt graphic frame to its text, which is placed where the frame
+    /// appears.
+    pub fn extract_text_with_diagrams(&self, diagram_text: DiagramText<'_>) -> Result<String> {
+        self.extract_text_filtered(SkipText::default(), diagram_text)
+    }
+
+    /// Extract the text content of the slide, leaving out table cells.
+    ///
+    /// Text inside `<a:tbl>` elements is skipped so that tables can be
+    /// rendered separately from the body text.
+    pub fn extract_text_without_tables(&self) -> Result<String> {
+        self.extract_text_filtered(
+            SkipText {
+                tables: true,
+                ..SkipText::default()
+            },
+            &|_| Ok(None),
+        )
+    }
+
+    /// Extract the text content of the slide, leaving out the title.
+    ///
+    /// Text of shapes holding a `title` or `ctrTitle` placeholder is skipped.
+    pub fn extract_body_text(&self) -> Result<String> {
+        self.extract_body_text_with_diagrams(&|_| Ok(None))
+    }
+
+    /// Extract the text content of the slide without the title, including
+    /// SmartArt text as in [`SlidePart::extract_text_with_diagrams`].
+    pub fn extract_body_text_with_diagrams(&self, diagram_text: DiagramText<'_>) -> Result<String> {
+        self.extract_text_filtered(
+            SkipText {
+                titles: true,
+                ..SkipText::default()
+            },
+            diagram_text,
+        )
+    }
+
+    fn extract_text_filtered(
+        &self,
+        skip: SkipText,
+        diagram_text: DiagramText<'_>,
+    ) -> Result<String> {
+        let _span = trace::debug_span!(
+            "parse_slide",
+            part = %self.part.partname(),
+            size = self.xml_bytes().len()
+        )
+        .entered();
         let mut reader = Reader::from_reader(self.xml_bytes());
         reader.config_mut().trim_text(true);
 
         let mut text = String::new();
         let mut in_text_element = false;
+        let mut table_depth = 0usize;
+        // Text length at the start of the current p:sp, and whether it is a title
+        let mut shape: Option<(usize, bool)> = None;
 
         loop {
             match reader.read_event() {
-                Ok(Event::Start(e)) => {
+                Ok(Event::Start(e)) => match e.local_name().as_ref() {
                     // Check if this is an a:t element (DrawingML text)
-                    if e.local_name().as_ref() == b"t" {
-                        in_text_element = true;
-                    }
+                    b"t" => in_text_element = true,
+                    b"tbl" if skip.tables => table_depth += 1,
+                    b"sp" if skip.titles => shape = Some((text.len(), false)),
+                    b"ph" => Self::mark_title_shape(&e, &mut shape),
+                    _ => {},
                 },
-                Ok(Event::Text(e)) if in_text_element => {
+                Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    b"ph" => Self::mark_title_shape(&e, &mut shape),
+                    b"relIds" => {
+                        if let Some(rel_id) = data_rel_id(&e)
+                            && let Some(diagram) = diagram_text(&rel_id)?
+                            && !diagram.is_empty()
+                        {
+                            if !text.is_empty() && !text.ends_with('\n') {
+                                text.push('\n');
+                            }
+                            text.push_str(&diagram);
+                        }
+                    },
+                    _ => {},
+                },
+                Ok(Event::Text(e)) if in_text_element && table_depth == 0 => {
                     // Extract text content
                     let t = std::str::from_utf8(e.as_ref())
                         .map_err(|e| OoxmlError::Xml(e.to_string()))?;
@@ -83,10 +177,15 @@ impl<'a> SlidePart<'a> {
                     }
                     text.push_str(t);
                 },
-                Ok(Event::End(e)) => {
-                    if e.local_name().as_ref() == b"t" {
-                        in_text_element = false;
-                    }
+                Ok(Event::End(e)) => match e.local_name().as_ref() {
+                    b"t" => in_text_element = false,
+                    b"tbl" if skip.tables => table_depth = table_depth.saturating_sub(1),
+                    b"sp" => {
+                        if let Some((start, true)) = shape.take() {
+                            text.truncate(start);
+                        }
+                    },
+                    _ => {},
                 },
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(OoxmlError::Xml(e.to_string())),
@@ -97,6 +196,85 @@ impl<'a> SlidePart<'a> {
         Ok(text)
     }
 
+    /// Report the text of the slide to `visitor` while parsing, stopping as
+    /// soon as it returns [`ControlFlow::Break`].
+    ///
+    /// Reports the text of
+    /// [`extract_text_with_diagrams`](Self::extract_text_with_diagrams) as
+    /// paragraphs, with table structure; see [`TextEvent`].
+    pub fn walk_text(
+        &self,
+        diagram_text: DiagramText<'_>,
+        visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        let _span = trace::debug_span!(
+            "parse_slide",
+            part = %self.part.partname(),
+            size = self.xml_bytes().len()
+        )
+        .entered();
+        let mut reader = Reader::from_reader(self.xml_bytes());
+        reader.config_mut().trim_text(false);
+
+        let mut in_text_element = false;
+        let mut chars = String::new();
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                    b"p" => visit!(visitor, TextEvent::ParagraphStart),
+                    b"t" => in_text_element = true,
+                    b"tbl" => visit!(visitor, TextEvent::TableStart),
+                    b"tr" => visit!(visitor, TextEvent::TableRowStart),
+                    b"tc" => visit!(visitor, TextEvent::TableCellStart),
+                    _ => {},
+                },
+                Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    b"br" => visit!(visitor, TextEvent::Text("\n")),
+                    b"relIds" => {
+                        if let Some(rel_id) = data_rel_id(&e)
+                            && let Some(diagram) = diagram_text(&rel_id)?
+                            && visit_lines(&diagram, visitor).is_break()
+                        {
+                            return Ok(ControlFlow::Break(()));
+                        }
+                    },
+                    _ => {},
+                },
+                Ok(Event::Text(e)) if in_text_element => {
+                    let text = std::str::from_utf8(e.as_ref())
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    visit!(visitor, TextEvent::Text(text));
+                },
+                Ok(Event::GeneralRef(e)) if in_text_element => {
+                    chars.clear();
+                    push_general_ref(&mut chars, &e)?;
+                    visit!(visitor, TextEvent::Text(&chars));
+                },
+                Ok(Event::End(e)) => match e.local_name().as_ref() {
+                    b"t" => in_text_element = false,
+                    b"tbl" => visit!(visitor, TextEvent::TableEnd),
+                    _ => {},
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Mark the current shape as a title if `ph` is a title placeholder.
+    fn mark_title_shape(ph: &BytesStart<'_>, shape: &mut Option<(usize, bool)>) {
+        if let Some((_, is_title)) = shape {
+            *is_title = ph.attributes().flatten().any(|attr| {
+                attr.key.as_ref() == b"type"
+                    && matches!(attr.value.as_ref(), b"title" | b"ctrTitle")
+            });
+        }
+    }
+
     /// Get the underlying OPC part.
     #[inline]
     pub fn part(&self) -> &'a dyn Part {
@@ -108,6 +286,12 @@ impl<'a> SlidePart<'a> {
     /// Returns a vector of BaseShape objects that can be checked for type
     /// and converted to specific shape types.
     pub fn shapes(&self) -> Result<Vec<BaseShape>> {
+        let _span = trace::debug_span!(
+            "parse_slide",
+            part = %self.part.partname(),
+            size = self.xml_bytes().len()
+        )
+        .entered();
         let mut reader = Reader::from_reader(self.xml_bytes());
         reader.config_mut().trim_text(true);
 
@@ -134,7 +318,7 @@ impl<'a> SlidePart<'a> {
                         let mut shape_buf = Vec::new();
                         // Extract the complete shape XML
                         if let Ok(shape_xml) =
-                            Self::extract_shape_xml(&mut reader, tag_name_bytes, &mut shape_buf)
+                            Self::extract_shape_xml(&mut reader, e.name().as_ref(), &mut shape_buf)
                         {
                             shapes.push(BaseShape::new(shape_xml, st));
                         }
@@ -191,6 +375,11 @@ impl<'a> SlidePart<'a> {
                 Ok(Event::Text(e)) => {
                     shape_xml.extend_from_slice(e.as_ref());
                 },
+                Ok(Event::GeneralRef(e)) => {
+                    shape_xml.push(b'&');
+                    shape_xml.extend_from_slice(e.as_ref());
+                    shape_xml.push(b';');
+                },
                 Ok(Event::Empty(e)) => {
                     shape_xml.push(b'<');
                     shape_xml.extend_from_slice(e.name().as_ref());
@@ -240,6 +429,7 @@ pub struct SlideLayoutPart<'a> {
 impl<'a> SlideLayoutPart<'a> {
     /// Create a SlideLayoutPart from an OPC Part.
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        part.try_blob()?;
         Ok(Self { part })
     }
 
@@ -294,6 +484,7 @@ pub struct SlideMasterPart<'a> {
 impl<'a> SlideMasterPart<'a> {
     /// Create a SlideMasterPart from an OPC Part.
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        part.try_blob()?;
         Ok(Self { part })
     }
 
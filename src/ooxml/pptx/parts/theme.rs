@@ -1,6 +1,8 @@
 /// Theme parts for PowerPoint presentations.
 ///
 /// This module provides types for working with themes in PPTX files.
+use crate::common::RGBColor;
+use crate::ooxml::drawings::color::{ColorScheme, ThemeColorSlot};
 use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::part::Part;
 use quick_xml::Reader;
@@ -39,6 +41,31 @@ pub struct Theme {
     pub colors: Vec<ThemeColor>,
 }
 
+impl Theme {
+    /// Build the color scheme used to resolve theme colors of shapes.
+    ///
+    /// System colors map to black (`windowText`) or white (`window`), and
+    /// slots the theme does not define keep the default Office colors.
+    pub fn color_scheme(&self) -> ColorScheme {
+        let mut scheme = ColorScheme::default();
+        for color in &self.colors {
+            let Some(slot) = ThemeColorSlot::from_scheme_name(&color.name) else {
+                continue;
+            };
+            let rgb = match (&color.rgb, color.system_color.as_deref()) {
+                (Some(rgb), _) => RGBColor::from_hex(rgb).ok(),
+                (None, Some("window")) => Some(RGBColor::new(0xFF, 0xFF, 0xFF)),
+                (None, Some(_)) => Some(RGBColor::new(0, 0, 0)),
+                (None, None) => None,
+            };
+            if let Some(rgb) = rgb {
+                scheme.set(slot, rgb);
+            }
+        }
+        scheme
+    }
+}
+
 /// Theme part - defines the visual styling of a presentation.
 ///
 /// Corresponds to `/ppt/theme/themeN.xml` in the package.
@@ -50,6 +77,7 @@ pub struct ThemePart<'a> {
 impl<'a> ThemePart<'a> {
     /// Create a ThemePart from an OPC Part.
     pub fn from_part(part: &'a dyn Part) -> Result<Self> {
+        part.try_blob()?;
         Ok(Self { part })
     }
 
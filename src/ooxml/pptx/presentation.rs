@@ -1,8 +1,9 @@
 /// Main presentation object - the high-level API for working with presentations.
-use crate::ooxml::error::Result;
+use crate::ooxml::error::{OoxmlError, Result};
 use crate::ooxml::opc::OpcPackage;
 use crate::ooxml::opc::packuri::PackURI;
 use crate::ooxml::pptx::parts::{PresentationPart, SlideMasterPart, SlidePart};
+use crate::ooxml::pptx::protection::PresentationProtection;
 use crate::ooxml::pptx::slide::{Slide, SlideMaster};
 
 /// A PowerPoint presentation.
@@ -72,12 +73,13 @@ impl<'a> Presentation<'a> {
     /// # Examples
     ///
     /// ```rust,no_run
+    /// use litchi::common::Length;
     /// use litchi::ooxml::pptx::Package;
     ///
     /// let pkg = Package::open("presentation.pptx")?;
     /// let pres = pkg.presentation()?;
     /// if let Some(width) = pres.slide_width()? {
-    ///     let inches = width as f64 / 914400.0;
+    ///     let inches = Length::from_emus(width).inches();
     ///     println!("Slide width: {:.2} inches", inches);
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
@@ -93,12 +95,13 @@ impl<'a> Presentation<'a> {
     /// # Examples
     ///
     /// ```rust,no_run
+    /// use litchi::common::Length;
     /// use litchi::ooxml::pptx::Package;
     ///
     /// let pkg = Package::open("presentation.pptx")?;
     /// let pres = pkg.presentation()?;
     /// if let Some(height) = pres.slide_height()? {
-    ///     let inches = height as f64 / 914400.0;
+    ///     let inches = Length::from_emus(height).inches();
     ///     println!("Slide height: {:.2} inches", inches);
     /// }
     /// # Ok::<(), Box<dyn std::error::Error>>(())
@@ -202,6 +205,30 @@ impl<'a> Presentation<'a> {
         self.package
     }
 
+    /// Get the modify password protection declared in presentation.xml.
+    ///
+    /// The read-only recommendation and the "final" mark are stored in the
+    /// document properties instead; see
+    /// [`Metadata::security`](crate::common::Metadata::security).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::pptx::Package;
+    ///
+    /// let pkg = Package::open("presentation.pptx")?;
+    /// let pres = pkg.presentation()?;
+    /// if pres.protection()?.modify_password_protected {
+    ///     println!("A password is needed to save changes");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn protection(&self) -> Result<PresentationProtection> {
+        let xml = std::str::from_utf8(self.part.part().try_blob()?)
+            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        PresentationProtection::parse_xml(xml)
+    }
+
     // ========================================================================
     // Slide Size Manipulation
     // ========================================================================
@@ -716,7 +743,7 @@ impl<'a> Presentation<'a> {
             }
 
             // Also parse inline hyperlinks from slide XML (internal slide links)
-            let slide_xml = slide_part.blob();
+            let slide_xml = slide_part.try_blob()?;
             if let Ok(inline_links) = Self::parse_inline_hyperlinks(slide_xml) {
                 for hyperlink in inline_links {
                     all_hyperlinks.push((slide_idx, hyperlink));
@@ -803,7 +830,7 @@ impl<'a> Presentation<'a> {
         use quick_xml::Reader;
         use quick_xml::events::Event;
 
-        let xml = self.part.part().blob();
+        let xml = self.part.part().try_blob()?;
         let mut reader = Reader::from_reader(xml);
         reader.config_mut().trim_text(true);
 
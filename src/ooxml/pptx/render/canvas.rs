@@ -0,0 +1,387 @@
+//! Raster canvas for slide rendering.
+//!
+//! Shapes are anti-aliased from the signed distance of each pixel center to
+//! the shape outline, measured in the shape's own unrotated coordinates.
+
+use crate::common::RGBColor;
+use image::{Rgb, RgbImage, RgbaImage, imageops::FilterType};
+
+/// Position of a shape on the canvas, in pixels.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(super) struct Frame {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Clockwise rotation about the center, in degrees
+    pub rotation: f32,
+    pub flip_h: bool,
+    pub flip_v: bool,
+}
+
+impl Frame {
+    fn center(&self) -> (f32, f32) {
+        (self.x + self.width / 2.0, self.y + self.height / 2.0)
+    }
+
+    /// Map a canvas point to coordinates relative to the frame center,
+    /// undoing the rotation.
+    fn local_point(&self, x: f32, y: f32) -> (f32, f32) {
+        let (cx, cy) = self.center();
+        let (dx, dy) = (x - cx, y - cy);
+        let (sin, cos) = (-self.rotation.to_radians()).sin_cos();
+        (dx * cos - dy * sin, dx * sin + dy * cos)
+    }
+
+    /// Pixels covered by the rotated frame grown by `margin`, clipped to a
+    /// canvas of `width` x `height`. Returns `(x0, y0, x1, y1)`, exclusive
+    /// at the end.
+    fn pixel_range(&self, margin: f32, width: u32, height: u32) -> Option<(u32, u32, u32, u32)> {
+        let (cx, cy) = self.center();
+        let (sin, cos) = self.rotation.to_radians().sin_cos();
+        let half_width = (self.width * cos).abs() / 2.0 + (self.height * sin).abs() / 2.0 + margin;
+        let half_height = (self.width * sin).abs() / 2.0 + (self.height * cos).abs() / 2.0 + margin;
+
+        let x0 = (cx - half_width).floor().max(0.0);
+        let y0 = (cy - half_height).floor().max(0.0);
+        let x1 = (cx + half_width).ceil().min(width as f32);
+        let y1 = (cy + half_height).ceil().min(height as f32);
+        (x0 < x1 && y0 < y1).then_some((x0 as u32, y0 as u32, x1 as u32, y1 as u32))
+    }
+}
+
+/// Outline of a shape.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) enum Geometry {
+    Rect,
+    /// Rectangle with corners rounded to `radius` pixels
+    RoundRect {
+        radius: f32,
+    },
+    Ellipse,
+    /// Line from the top-left to the bottom-right corner of the frame,
+    /// mirrored by the frame flips
+    Line,
+}
+
+impl Geometry {
+    /// Signed distance in pixels from a point relative to the frame center
+    /// to the outline, negative inside.
+    fn distance(&self, frame: &Frame, (x, y): (f32, f32)) -> f32 {
+        let (half_width, half_height) = (frame.width / 2.0, frame.height / 2.0);
+        match *self {
+            Self::Rect => box_distance(x, y, half_width, half_height, 0.0),
+            Self::RoundRect { radius } => {
+                let radius = radius.min(half_width).min(half_height).max(0.0);
+                box_distance(x, y, half_width, half_height, radius)
+            },
+            Self::Ellipse => ellipse_distance(x, y, half_width, half_height),
+            Self::Line => {
+                let end_x = if frame.flip_h {
+                    -half_width
+                } else {
+                    half_width
+                };
+                let end_y = if frame.flip_v {
+                    -half_height
+                } else {
+                    half_height
+                };
+                segment_distance(x, y, (-end_x, -end_y), (end_x, end_y))
+            },
+        }
+    }
+}
+
+/// Signed distance to a centered box with rounded corners
+fn box_distance(x: f32, y: f32, half_width: f32, half_height: f32, radius: f32) -> f32 {
+    let qx = x.abs() - (half_width - radius);
+    let qy = y.abs() - (half_height - radius);
+    let outside = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt();
+    outside + qx.max(qy).min(0.0) - radius
+}
+
+/// Approximate signed distance to a centered ellipse, exact on the outline
+fn ellipse_distance(x: f32, y: f32, a: f32, b: f32) -> f32 {
+    if a <= 0.0 || b <= 0.0 {
+        return box_distance(x, y, a.max(0.0), b.max(0.0), 0.0);
+    }
+    let value = (x / a).powi(2) + (y / b).powi(2) - 1.0;
+    let gradient = 2.0 * ((x / (a * a)).powi(2) + (y / (b * b)).powi(2)).sqrt();
+    if gradient < f32::EPSILON {
+        return -a.min(b);
+    }
+    value / gradient
+}
+
+/// Distance to the segment from `a` to `b`
+fn segment_distance(x: f32, y: f32, a: (f32, f32), b: (f32, f32)) -> f32 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length = dx * dx + dy * dy;
+    let t = if length > 0.0 {
+        (((x - a.0) * dx + (y - a.1) * dy) / length).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    ((x - a.0 - t * dx).powi(2) + (y - a.1 - t * dy).powi(2)).sqrt()
+}
+
+/// An RGB image that shapes, pictures and glyphs are painted onto.
+pub(super) struct Canvas {
+    image: RgbImage,
+}
+
+impl Canvas {
+    /// Create a canvas filled with `background`.
+    pub fn new(width: u32, height: u32, background: RGBColor) -> Self {
+        Self {
+            image: RgbImage::from_pixel(width, height, rgb(background)),
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.image.width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.image.height()
+    }
+
+    pub fn into_image(self) -> RgbImage {
+        self.image
+    }
+
+    /// Blend `color` over a pixel with opacity `alpha` (0.0 to 1.0).
+    #[inline]
+    fn blend(&mut self, x: u32, y: u32, color: RGBColor, alpha: f32) {
+        if alpha <= 0.0 {
+            return;
+        }
+        let pixel = self.image.get_pixel_mut(x, y);
+        if alpha >= 1.0 {
+            *pixel = rgb(color);
+            return;
+        }
+        for (channel, value) in pixel.0.iter_mut().zip([color.r, color.g, color.b]) {
+            *channel = (value as f32 * alpha + *channel as f32 * (1.0 - alpha)).round() as u8;
+        }
+    }
+
+    /// Fill the whole canvas with a gradient.
+    ///
+    /// `stops` are `(position, color)` pairs with positions from 0.0 to 1.0.
+    /// Linear gradients run along `angle` degrees clockwise from left to
+    /// right; radial gradients run from the center to the corners.
+    pub fn fill_gradient(&mut self, stops: &[(f32, RGBColor)], angle: f32, radial: bool) {
+        if stops.is_empty() {
+            return;
+        }
+        let (width, height) = (self.width() as f32, self.height() as f32);
+        let (cx, cy) = (width / 2.0, height / 2.0);
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let half_extent = ((width * cos).abs() + (height * sin).abs()) / 2.0;
+        let radius = (cx * cx + cy * cy).sqrt();
+
+        for (x, y, pixel) in self.image.enumerate_pixels_mut() {
+            let (dx, dy) = (x as f32 + 0.5 - cx, y as f32 + 0.5 - cy);
+            let t = if radial {
+                (dx * dx + dy * dy).sqrt() / radius
+            } else if half_extent > 0.0 {
+                ((dx * cos + dy * sin) / half_extent + 1.0) / 2.0
+            } else {
+                0.0
+            };
+            *pixel = rgb(gradient_color(stops, t));
+        }
+    }
+
+    /// Fill the inside of a shape.
+    pub fn fill(&mut self, frame: &Frame, geometry: Geometry, color: RGBColor) {
+        if geometry == Geometry::Line {
+            return;
+        }
+        self.paint(frame, geometry, 1.0, color, |distance| {
+            (0.5 - distance).clamp(0.0, 1.0)
+        });
+    }
+
+    /// Draw the outline of a shape, `width` pixels wide.
+    pub fn stroke(&mut self, frame: &Frame, geometry: Geometry, color: RGBColor, width: f32) {
+        // Lines thinner than a pixel are drawn as a lighter pixel-wide line
+        let opacity = width.min(1.0);
+        let half_width = width.max(1.0) / 2.0;
+        self.paint(frame, geometry, half_width + 1.0, color, |distance| {
+            (half_width + 0.5 - distance.abs()).clamp(0.0, 1.0) * opacity
+        });
+    }
+
+    /// Paint the pixels around a frame with the coverage computed from
+    /// their distance to the outline.
+    fn paint(
+        &mut self,
+        frame: &Frame,
+        geometry: Geometry,
+        margin: f32,
+        color: RGBColor,
+        coverage: impl Fn(f32) -> f32,
+    ) {
+        let Some((x0, y0, x1, y1)) = frame.pixel_range(margin, self.width(), self.height()) else {
+            return;
+        };
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let local = frame.local_point(x as f32 + 0.5, y as f32 + 0.5);
+                let alpha = coverage(geometry.distance(frame, local));
+                self.blend(x, y, color, alpha);
+            }
+        }
+    }
+
+    /// Draw a picture stretched to a frame.
+    pub fn draw_image(&mut self, frame: &Frame, picture: &RgbaImage) {
+        let (width, height) = (frame.width.round() as u32, frame.height.round() as u32);
+        if width == 0 || height == 0 || picture.width() == 0 || picture.height() == 0 {
+            return;
+        }
+        let scaled = image::imageops::resize(picture, width, height, FilterType::Triangle);
+        let Some((x0, y0, x1, y1)) = frame.pixel_range(0.0, self.width(), self.height()) else {
+            return;
+        };
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let (lx, ly) = frame.local_point(x as f32 + 0.5, y as f32 + 0.5);
+                let mut u = lx + frame.width / 2.0;
+                let mut v = ly + frame.height / 2.0;
+                if frame.flip_h {
+                    u = frame.width - u;
+                }
+                if frame.flip_v {
+                    v = frame.height - v;
+                }
+                if u < 0.0 || v < 0.0 || u >= width as f32 || v >= height as f32 {
+                    continue;
+                }
+                let source = scaled.get_pixel(u as u32, v as u32).0;
+                let color = RGBColor::new(source[0], source[1], source[2]);
+                self.blend(x, y, color, source[3] as f32 / 255.0);
+            }
+        }
+    }
+
+    /// Paint `color` through an 8-bit coverage mask, such as a rasterized
+    /// glyph, whose top-left corner is at `(x, y)`.
+    pub fn draw_mask(
+        &mut self,
+        (x, y): (i32, i32),
+        (width, height): (usize, usize),
+        stride: usize,
+        mask: &[u8],
+        color: RGBColor,
+    ) {
+        for row in 0..height {
+            let py = y + row as i32;
+            if py < 0 || py >= self.height() as i32 {
+                continue;
+            }
+            for col in 0..width {
+                let px = x + col as i32;
+                if px < 0 || px >= self.width() as i32 {
+                    continue;
+                }
+                if let Some(&value) = mask.get(row * stride + col) {
+                    self.blend(px as u32, py as u32, color, value as f32 / 255.0);
+                }
+            }
+        }
+    }
+}
+
+/// Interpolate the color at position `t` of a gradient
+fn gradient_color(stops: &[(f32, RGBColor)], t: f32) -> RGBColor {
+    let t = t.clamp(0.0, 1.0);
+    let mut previous = stops[0];
+    if t <= previous.0 {
+        return previous.1;
+    }
+    for &stop in &stops[1..] {
+        if t <= stop.0 {
+            let span = stop.0 - previous.0;
+            let f = if span > 0.0 {
+                (t - previous.0) / span
+            } else {
+                1.0
+            };
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * f).round() as u8;
+            return RGBColor::new(
+                mix(previous.1.r, stop.1.r),
+                mix(previous.1.g, stop.1.g),
+                mix(previous.1.b, stop.1.b),
+            );
+        }
+        previous = stop;
+    }
+    previous.1
+}
+
+#[inline]
+fn rgb(color: RGBColor) -> Rgb<u8> {
+    Rgb([color.r, color.g, color.b])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHITE: RGBColor = RGBColor::new(0xFF, 0xFF, 0xFF);
+    const RED: RGBColor = RGBColor::new(0xFF, 0, 0);
+
+    fn pixel(canvas: &Canvas, x: u32, y: u32) -> [u8; 3] {
+        canvas.image.get_pixel(x, y).0
+    }
+
+    #[test]
+    fn test_fill_ellipse_antialiased() {
+        let mut canvas = Canvas::new(40, 40, WHITE);
+        let frame = Frame {
+            x: 10.0,
+            y: 10.0,
+            width: 20.0,
+            height: 20.0,
+            ..Frame::default()
+        };
+        canvas.fill(&frame, Geometry::Ellipse, RED);
+
+        assert_eq!(pixel(&canvas, 20, 20), [0xFF, 0, 0]);
+        // The frame corner is outside the circle
+        assert_eq!(pixel(&canvas, 10, 10), [0xFF, 0xFF, 0xFF]);
+        // Edge pixels are partly covered
+        let edge = pixel(&canvas, 29, 16);
+        assert!(edge[1] > 0 && edge[1] < 0xFF, "{:?}", edge);
+    }
+
+    #[test]
+    fn test_stroke_rotated_line() {
+        let mut canvas = Canvas::new(40, 40, WHITE);
+        // A horizontal line rotated a quarter turn becomes vertical
+        let frame = Frame {
+            x: 0.0,
+            y: 20.0,
+            width: 40.0,
+            height: 0.0,
+            rotation: 90.0,
+            ..Frame::default()
+        };
+        canvas.stroke(&frame, Geometry::Line, RED, 2.0);
+
+        assert_eq!(pixel(&canvas, 20, 5), [0xFF, 0, 0]);
+        assert_eq!(pixel(&canvas, 5, 20), [0xFF, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn test_gradient_color() {
+        let stops = [(0.0, RED), (1.0, WHITE)];
+        assert_eq!(gradient_color(&stops, 0.0), RED);
+        assert_eq!(gradient_color(&stops, 0.5), RGBColor::new(0xFF, 0x80, 0x80));
+        assert_eq!(gradient_color(&stops, 2.0), WHITE);
+    }
+}
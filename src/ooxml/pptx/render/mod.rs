@@ -0,0 +1,787 @@
+//! Slide rendering to PNG thumbnails.
+//!
+//! [`Slide::render_to_png`] draws a readable preview of a slide without an
+//! office suite:
+//!
+//! - the slide background, from the slide, its layout or its master, as a
+//!   solid color or a linear or radial gradient;
+//! - shape fills and outlines for rectangles, rounded rectangles, ellipses
+//!   and lines, with rotation; other preset geometries are drawn as their
+//!   bounding rectangle;
+//! - shape and table text, wrapped at the shape width with a system
+//!   sans-serif font in the size, color, weight and style of each run;
+//! - pictures, stretched to their frame.
+//!
+//! Perfect fidelity is not the goal. Font families, bullets, effects,
+//! charts, picture and pattern fills and inherited text formatting other
+//! than placeholder positions are not drawn.
+
+mod canvas;
+mod text;
+
+use self::canvas::{Canvas, Frame, Geometry};
+use self::text::{Align, Anchor, Span, TextArea, TextParagraph};
+use crate::common::xml::attr;
+use crate::common::{RGBColor, Rect};
+use crate::document::displayable;
+use crate::ooxml::docx::format::ImageFormat;
+use crate::ooxml::drawings::color::{ColorScheme, ColorSpec, ThemeColorSlot, read_fill_color};
+use crate::ooxml::error::{OoxmlError, Result};
+use crate::ooxml::opc::Part;
+use crate::ooxml::opc::constants::relationship_type as rt;
+use crate::ooxml::pptx::backgrounds::{GradientType, SlideBackground};
+use crate::ooxml::pptx::parts::PresentationPart;
+use crate::ooxml::pptx::shapes::{BaseShape, Picture, ShapeType, TextFrame};
+use crate::ooxml::pptx::slide::{Slide, related_part};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+/// Slide size of PowerPoint's 4:3 default (10 x 7.5 inches)
+const DEFAULT_SLIDE_SIZE: (i64, i64) = (9_144_000, 6_858_000);
+/// EMUs per point
+const EMUS_PER_POINT: f64 = 12_700.0;
+/// Default left and right text insets (0.1 inch)
+const DEFAULT_INSET_X: i64 = 91_440;
+/// Default top and bottom text insets (0.05 inch)
+const DEFAULT_INSET_Y: i64 = 45_720;
+/// Default outline width (0.75 point)
+const DEFAULT_LINE_WIDTH: i64 = 9_525;
+/// Font size of table cell text, in points
+const TABLE_FONT_SIZE: f64 = 14.0;
+
+impl Slide<'_> {
+    /// Render the slide to a PNG image `width` pixels wide.
+    ///
+    /// The height follows the slide's aspect ratio. This is a best-effort
+    /// thumbnail; see the [`render`](crate::ooxml::pptx::render) module for
+    /// what is drawn.
+    ///
+    /// **Note**: This requires the `render` feature to be enabled.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::pptx::Package;
+    ///
+    /// let pkg = Package::open("presentation.pptx")?;
+    /// let pres = pkg.presentation()?;
+    ///
+    /// for (index, slide) in pres.slides()?.iter().enumerate() {
+    ///     let png = slide.render_to_png(320)?;
+    ///     std::fs::write(format!("slide{}.png", index + 1), png)?;
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn render_to_png(&self, width: u32) -> Result<Vec<u8>> {
+        if width == 0 {
+            return Err(OoxmlError::Other(
+                "thumbnail width must be positive".to_string(),
+            ));
+        }
+        let image = Renderer::new(self, width)?.render()?;
+
+        let mut png = Vec::new();
+        image
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| OoxmlError::Other(e.to_string()))?;
+        Ok(png)
+    }
+}
+
+/// Maps the child coordinates of a group to slide coordinates, in EMUs.
+#[derive(Debug, Clone, Copy)]
+struct GroupTransform {
+    scale_x: f64,
+    scale_y: f64,
+    offset_x: f64,
+    offset_y: f64,
+}
+
+impl GroupTransform {
+    const IDENTITY: Self = Self {
+        scale_x: 1.0,
+        scale_y: 1.0,
+        offset_x: 0.0,
+        offset_y: 0.0,
+    };
+
+    fn map(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            x * self.scale_x + self.offset_x,
+            y * self.scale_y + self.offset_y,
+        )
+    }
+
+    /// The transform of a group nested in this one, from its `a:xfrm`
+    /// offset, extent, child offset and child extent.
+    fn nested(&self, xfrm: [i64; 8]) -> Self {
+        let [x, y, cx, cy, ch_x, ch_y, ch_cx, ch_cy] = xfrm.map(|v| v as f64);
+        let scale_x = if ch_cx > 0.0 { cx / ch_cx } else { 1.0 };
+        let scale_y = if ch_cy > 0.0 { cy / ch_cy } else { 1.0 };
+        Self {
+            scale_x: self.scale_x * scale_x,
+            scale_y: self.scale_y * scale_y,
+            offset_x: self.offset_x + self.scale_x * (x - ch_x * scale_x),
+            offset_y: self.offset_y + self.scale_y * (y - ch_y * scale_y),
+        }
+    }
+}
+
+/// Draws one slide onto a canvas.
+struct Renderer<'s, 'a> {
+    slide: &'s Slide<'a>,
+    layout: Option<&'a dyn Part>,
+    master: Option<&'a dyn Part>,
+    scheme: ColorScheme,
+    /// Pixels per EMU
+    scale: f64,
+    canvas: Canvas,
+}
+
+impl<'s, 'a> Renderer<'s, 'a> {
+    fn new(slide: &'s Slide<'a>, width: u32) -> Result<Self> {
+        let package = slide.package();
+        let (mut layout, mut master, mut scheme, mut size) =
+            (None, None, ColorScheme::default(), None);
+        if let Some(package) = package {
+            layout = related_part(package, slide.part().part(), rt::SLIDE_LAYOUT)?;
+            master = match layout {
+                Some(layout) => related_part(package, layout, rt::SLIDE_MASTER)?,
+                None => None,
+            };
+            if let Some(master) = master
+                && let Some(theme) = related_part(package, master, rt::THEME)?
+            {
+                scheme = ColorScheme::from_theme_xml(theme.try_blob()?)?;
+            }
+            if let Ok(part) = package.main_document_part() {
+                let presentation = PresentationPart::from_part(part)?;
+                size = presentation
+                    .slide_width()?
+                    .zip(presentation.slide_height()?)
+                    .filter(|&(cx, cy)| cx > 0 && cy > 0);
+            }
+        }
+
+        let (slide_width, slide_height) = size.unwrap_or(DEFAULT_SLIDE_SIZE);
+        let scale = width as f64 / slide_width as f64;
+        let height = ((slide_height as f64 * scale).round() as u32).max(1);
+        let canvas = Canvas::new(width, height, scheme.get(ThemeColorSlot::Light1));
+        Ok(Self {
+            slide,
+            layout,
+            master,
+            scheme,
+            scale,
+            canvas,
+        })
+    }
+
+    fn render(mut self) -> Result<image::RgbImage> {
+        self.draw_background()?;
+        for shape in self.slide.shapes()? {
+            self.draw_shape(shape, None)?;
+        }
+        Ok(self.canvas.into_image())
+    }
+
+    /// Draw the background of the slide, or else of its layout or master.
+    fn draw_background(&mut self) -> Result<()> {
+        let mut background = self.slide.background()?;
+        for part in [self.layout, self.master].into_iter().flatten() {
+            if background.is_some() {
+                break;
+            }
+            background = SlideBackground::from_xml(part.try_blob()?)?;
+        }
+
+        match background {
+            Some(SlideBackground::Solid { color }) => {
+                if let Some(color) = self.named_color(&color) {
+                    self.canvas = Canvas::new(self.canvas.width(), self.canvas.height(), color);
+                }
+            },
+            Some(SlideBackground::Gradient {
+                gradient_type,
+                angle,
+                stops,
+            }) => {
+                let mut stops: Vec<(f32, RGBColor)> = stops
+                    .iter()
+                    .filter_map(|stop| Some((stop.position as f32, self.named_color(&stop.color)?)))
+                    .collect();
+                stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+                let radial = gradient_type != GradientType::Linear;
+                self.canvas
+                    .fill_gradient(&stops, angle.unwrap_or(0.0) as f32, radial);
+            },
+            Some(SlideBackground::Pattern { bg_color, .. }) => {
+                if let Some(color) = self.named_color(&bg_color) {
+                    self.canvas = Canvas::new(self.canvas.width(), self.canvas.height(), color);
+                }
+            },
+            _ => {},
+        }
+        Ok(())
+    }
+
+    /// Resolve a background color: hexadecimal RGB or a scheme color name.
+    fn named_color(&self, value: &str) -> Option<RGBColor> {
+        RGBColor::from_hex(value)
+            .ok()
+            .or_else(|| ThemeColorSlot::from_scheme_name(value).map(|slot| self.scheme.get(slot)))
+    }
+
+    /// Draw a shape, with the coordinates of shapes in a group mapped by
+    /// `group`.
+    fn draw_shape(&mut self, mut shape: BaseShape, group: Option<GroupTransform>) -> Result<()> {
+        if *shape.shape_type() == ShapeType::GroupShape {
+            let (xfrm, children) = group_children(shape.xml_bytes())?;
+            let transform = group
+                .unwrap_or(GroupTransform::IDENTITY)
+                .nested(xfrm.unwrap_or([0, 0, 0, 0, 0, 0, 0, 0]));
+            for child in children {
+                self.draw_shape(child, Some(transform))?;
+            }
+            return Ok(());
+        }
+
+        // Only top-level placeholders inherit a position
+        let bounds = match group {
+            Some(_) => shape.bounds()?,
+            None => self.slide.shape_bounds(&mut shape)?,
+        };
+        let Some(bounds) = bounds else {
+            return Ok(());
+        };
+        let frame = Frame {
+            rotation: shape.rotation()? as f32,
+            flip_h: shape.flip_horizontal()?,
+            flip_v: shape.flip_vertical()?,
+            ..self.frame(&bounds, group.unwrap_or(GroupTransform::IDENTITY))
+        };
+
+        match shape.shape_type() {
+            ShapeType::Picture => self.draw_picture(&shape, &frame),
+            ShapeType::GraphicFrame => self.draw_table(&shape, &frame),
+            ShapeType::Shape | ShapeType::Connector => self.draw_geometry(&shape, &frame),
+            _ => Ok(()),
+        }
+    }
+
+    /// The pixel frame of shape bounds.
+    fn frame(&self, bounds: &Rect, group: GroupTransform) -> Frame {
+        let (x, y) = group.map(bounds.x().emus() as f64, bounds.y().emus() as f64);
+        let width = bounds.width().emus() as f64 * group.scale_x;
+        let height = bounds.height().emus() as f64 * group.scale_y;
+        Frame {
+            x: (x * self.scale) as f32,
+            y: (y * self.scale) as f32,
+            width: (width * self.scale) as f32,
+            height: (height * self.scale) as f32,
+            ..Frame::default()
+        }
+    }
+
+    /// Draw the fill, outline and text of a shape.
+    fn draw_geometry(&mut self, shape: &BaseShape, frame: &Frame) -> Result<()> {
+        let style = ShapeStyle::parse(shape.xml_bytes())?;
+        let geometry = style.geometry(frame);
+
+        if let Some(fill) = shape.fill_color(&self.scheme)? {
+            self.canvas.fill(frame, geometry, fill);
+        }
+        if let Some((color, width)) = style.outline(&self.scheme) {
+            let width = (width as f64 * self.scale) as f32;
+            self.canvas.stroke(frame, geometry, color, width);
+        }
+
+        if shape.has_text_frame() {
+            let default_size = style.default_font_size();
+            let default_color = style
+                .style_font
+                .as_ref()
+                .and_then(|color| color.resolve(&self.scheme))
+                .unwrap_or_else(|| self.scheme.get(ThemeColorSlot::Dark1));
+            let mut paragraphs = Vec::new();
+            for paragraph in TextFrame::from_xml(shape.xml_bytes())?.paragraphs()? {
+                let align = match paragraph.alignment().as_deref() {
+                    Some("ctr") => Align::Center,
+                    Some("r") => Align::Right,
+                    _ => Align::Left,
+                };
+                let spans = paragraph
+                    .runs()?
+                    .into_iter()
+                    .map(|run| Span {
+                        size: self
+                            .font_pixels(run.font_size.unwrap_or(default_size) * style.font_scale),
+                        color: run
+                            .color
+                            .and_then(|color| color.resolve(&self.scheme))
+                            .unwrap_or(default_color),
+                        bold: run.bold.unwrap_or(false),
+                        italic: run.italic.unwrap_or(false),
+                        underline: run.underline.unwrap_or(false),
+                        text: run.text,
+                    })
+                    .collect();
+                paragraphs.push(TextParagraph {
+                    spans,
+                    align,
+                    empty_size: self.font_pixels(default_size * style.font_scale),
+                });
+            }
+
+            let [left, top, right, bottom] =
+                style.insets.map(|inset| (inset as f64 * self.scale) as f32);
+            let area = TextArea {
+                x: frame.x + left,
+                y: frame.y + top,
+                width: (frame.width - left - right).max(0.0),
+                height: (frame.height - top - bottom).max(0.0),
+                wrap: style.wrap,
+                anchor: style.anchor,
+            };
+            text::draw_text(&mut self.canvas, &area, &paragraphs);
+        }
+        Ok(())
+    }
+
+    /// Draw a picture stretched to its frame.
+    fn draw_picture(&mut self, shape: &BaseShape, frame: &Frame) -> Result<()> {
+        let r_id = Picture::new(shape.xml_bytes().to_vec()).image_r_id()?;
+        let Some((_, data)) = self.slide.image(&r_id)? else {
+            return Ok(());
+        };
+        let Some(format) = ImageFormat::detect_from_bytes(data) else {
+            return Ok(());
+        };
+        // Metafiles are converted to PNG
+        let (data, _) = displayable(data.to_vec(), format.extension());
+        if let Ok(picture) = image::load_from_memory(&data) {
+            self.canvas.draw_image(frame, &picture.to_rgba8());
+        }
+        Ok(())
+    }
+
+    /// Draw a table as a grid of equal cells with their text.
+    fn draw_table(&mut self, shape: &BaseShape, frame: &Frame) -> Result<()> {
+        let Some(table) = shape.table()? else {
+            return Ok(());
+        };
+        let rows = table.rows()?;
+        let columns = table.column_count()?.max(1);
+        if rows.is_empty() {
+            return Ok(());
+        }
+        let cell_width = frame.width / columns as f32;
+        let cell_height = frame.height / rows.len() as f32;
+        let border = self.scheme.get(ThemeColorSlot::Dark1);
+        let inset = (DEFAULT_INSET_X as f64 * self.scale) as f32;
+
+        for (row_index, row) in rows.iter().enumerate() {
+            for (column, cell) in row.cells()?.iter().enumerate() {
+                let cell_frame = Frame {
+                    x: frame.x + column as f32 * cell_width,
+                    y: frame.y + row_index as f32 * cell_height,
+                    width: cell_width,
+                    height: cell_height,
+                    ..Frame::default()
+                };
+                self.canvas.stroke(&cell_frame, Geometry::Rect, border, 1.0);
+                if cell.is_h_merge()? || cell.is_v_merge()? {
+                    continue;
+                }
+                let paragraph = TextParagraph {
+                    spans: vec![Span {
+                        text: cell.text()?,
+                        size: self.font_pixels(TABLE_FONT_SIZE),
+                        color: border,
+                        bold: row_index == 0,
+                        italic: false,
+                        underline: false,
+                    }],
+                    align: Align::Left,
+                    empty_size: 0.0,
+                };
+                let area = TextArea {
+                    x: cell_frame.x + inset,
+                    y: cell_frame.y + inset / 2.0,
+                    width: (cell_width - 2.0 * inset).max(0.0),
+                    height: cell_height,
+                    wrap: true,
+                    anchor: Anchor::Top,
+                };
+                text::draw_text(&mut self.canvas, &area, &[paragraph]);
+            }
+        }
+        Ok(())
+    }
+
+    /// Convert a font size in points to pixels.
+    fn font_pixels(&self, points: f64) -> f32 {
+        (points * EMUS_PER_POINT * self.scale) as f32
+    }
+}
+
+/// Outline of a shape's `a:ln`.
+#[derive(Debug, Clone, Default, PartialEq)]
+enum Outline {
+    /// No `a:ln`; the shape style decides
+    #[default]
+    Unset,
+    /// `a:ln` with `a:noFill`
+    None,
+    /// `a:ln` with its color, if set, and width in EMUs
+    Line(Option<ColorSpec>, Option<i64>),
+}
+
+/// The properties of a shape that decide how it is drawn.
+#[derive(Debug, Clone)]
+struct ShapeStyle {
+    /// Preset geometry name (`a:prstGeom/@prst`)
+    preset: Option<String>,
+    /// First adjust value of the geometry, in 1/100000ths
+    adjust: Option<f64>,
+    outline: Outline,
+    /// Line color of the shape style (`p:style/a:lnRef`)
+    style_line: Option<ColorSpec>,
+    /// Text color of the shape style (`p:style/a:fontRef`)
+    style_font: Option<ColorSpec>,
+    /// Placeholder type, `obj` when the placeholder has none
+    placeholder: Option<String>,
+    /// Text insets (left, top, right, bottom) in EMUs
+    insets: [i64; 4],
+    wrap: bool,
+    anchor: Anchor,
+    /// Shrink factor of `a:normAutofit/@fontScale`
+    font_scale: f64,
+}
+
+impl Default for ShapeStyle {
+    fn default() -> Self {
+        Self {
+            preset: None,
+            adjust: None,
+            outline: Outline::Unset,
+            style_line: None,
+            style_font: None,
+            placeholder: None,
+            insets: [
+                DEFAULT_INSET_X,
+                DEFAULT_INSET_Y,
+                DEFAULT_INSET_X,
+                DEFAULT_INSET_Y,
+            ],
+            wrap: true,
+            anchor: Anchor::Top,
+            font_scale: 1.0,
+        }
+    }
+}
+
+impl ShapeStyle {
+    /// Read the style of a `p:sp` or `p:cxnSp` element.
+    fn parse(xml: &[u8]) -> Result<Self> {
+        let mut reader = Reader::from_reader(xml);
+        reader.config_mut().trim_text(true);
+
+        let mut style = Self::default();
+        let mut in_sp_pr = false;
+        let mut seen_sp_pr = false;
+        let mut in_ln = false;
+
+        loop {
+            let event = reader.read_event();
+            let is_empty = matches!(event, Ok(Event::Empty(_)));
+            match event {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    match e.local_name().as_ref() {
+                        b"spPr" if !seen_sp_pr => {
+                            seen_sp_pr = true;
+                            in_sp_pr = !is_empty;
+                        },
+                        b"prstGeom" if in_sp_pr => style.preset = attr(e, b"prst"),
+                        b"gd" if in_sp_pr => {
+                            style.adjust = style.adjust.or_else(|| {
+                                attr(e, b"fmla")
+                                    .and_then(|fmla| fmla.strip_prefix("val ")?.parse().ok())
+                            });
+                        },
+                        b"ln" if in_sp_pr => {
+                            let width = attr(e, b"w").and_then(|w| w.parse().ok());
+                            style.outline = Outline::Line(None, width);
+                            in_ln = !is_empty;
+                        },
+                        b"noFill" if in_ln => style.outline = Outline::None,
+                        b"solidFill" if in_ln && !is_empty => {
+                            let color = read_fill_color(&mut reader, b"solidFill")?;
+                            if let Outline::Line(line_color, _) = &mut style.outline {
+                                *line_color = color;
+                            }
+                        },
+                        b"lnRef" if !is_empty && attr(e, b"idx").as_deref() != Some("0") => {
+                            style.style_line = read_fill_color(&mut reader, b"lnRef")?;
+                        },
+                        b"fontRef" if !is_empty => {
+                            style.style_font = read_fill_color(&mut reader, b"fontRef")?;
+                        },
+                        b"ph" => {
+                            style.placeholder =
+                                Some(attr(e, b"type").unwrap_or_else(|| "obj".to_string()));
+                        },
+                        b"bodyPr" => style.read_body_properties(e),
+                        b"normAutofit" => {
+                            if let Some(scale) = attr(e, b"fontScale").and_then(|v| percentage(&v))
+                            {
+                                style.font_scale = scale;
+                            }
+                        },
+                        _ => {},
+                    }
+                },
+                Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                    b"spPr" => in_sp_pr = false,
+                    b"ln" => in_ln = false,
+                    _ => {},
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(style)
+    }
+
+    /// Read the insets, wrapping and anchor of `a:bodyPr`.
+    fn read_body_properties(&mut self, e: &BytesStart<'_>) {
+        for (index, name) in [b"lIns", b"tIns", b"rIns", b"bIns"].iter().enumerate() {
+            if let Some(inset) = attr(e, *name).and_then(|v| v.parse().ok()) {
+                self.insets[index] = inset;
+            }
+        }
+        if attr(e, b"wrap").as_deref() == Some("none") {
+            self.wrap = false;
+        }
+        self.anchor = match attr(e, b"anchor").as_deref() {
+            Some("ctr") => Anchor::Middle,
+            Some("b") => Anchor::Bottom,
+            _ => Anchor::Top,
+        };
+    }
+
+    /// The geometry to draw, from the preset name.
+    fn geometry(&self, frame: &Frame) -> Geometry {
+        let preset = self.preset.as_deref().unwrap_or("rect");
+        match preset {
+            "ellipse" => Geometry::Ellipse,
+            "roundRect" => {
+                let adjust = self.adjust.unwrap_or(16_667.0) / 100_000.0;
+                Geometry::RoundRect {
+                    radius: frame.width.min(frame.height) * adjust as f32,
+                }
+            },
+            "line" | "straightConnector1" => Geometry::Line,
+            _ if preset.starts_with("bentConnector") || preset.starts_with("curvedConnector") => {
+                Geometry::Line
+            },
+            _ => Geometry::Rect,
+        }
+    }
+
+    /// The outline color and width in EMUs, if the shape has an outline.
+    fn outline(&self, scheme: &ColorScheme) -> Option<(RGBColor, i64)> {
+        let (color, width) = match &self.outline {
+            Outline::None => return None,
+            Outline::Unset => (self.style_line.as_ref()?, None),
+            Outline::Line(color, width) => (color.as_ref().or(self.style_line.as_ref())?, *width),
+        };
+        Some((color.resolve(scheme)?, width.unwrap_or(DEFAULT_LINE_WIDTH)))
+    }
+
+    /// Font size in points for runs without one. Placeholders take rough
+    /// sizes of the default Office master, since master text styles are not
+    /// read.
+    fn default_font_size(&self) -> f64 {
+        match self.placeholder.as_deref() {
+            Some("title" | "ctrTitle") => 44.0,
+            Some("dt" | "ftr" | "sldNum") => 12.0,
+            Some(_) => 24.0,
+            None => 18.0,
+        }
+    }
+}
+
+/// Split a group into the `a:xfrm` of its `p:grpSpPr`, as offset, extent,
+/// child offset and child extent, and its child shapes.
+fn group_children(xml: &[u8]) -> Result<(Option<[i64; 8]>, Vec<BaseShape>)> {
+    let mut reader = Reader::from_reader(xml);
+
+    let mut xfrm: Option<[i64; 8]> = None;
+    let mut children = Vec::new();
+    let mut depth = 0usize;
+    let mut in_xfrm = false;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        match reader.read_event() {
+            Ok(Event::Start(e)) => {
+                let name = e.local_name();
+                let kind = match name.as_ref() {
+                    b"sp" => Some(ShapeType::Shape),
+                    b"pic" => Some(ShapeType::Picture),
+                    b"graphicFrame" => Some(ShapeType::GraphicFrame),
+                    b"grpSp" => Some(ShapeType::GroupShape),
+                    b"cxnSp" => Some(ShapeType::Connector),
+                    _ => None,
+                };
+                match kind {
+                    Some(kind) if depth == 1 => {
+                        reader
+                            .read_to_end(e.name())
+                            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                        let end = reader.buffer_position() as usize;
+                        children.push(BaseShape::new(xml[start..end].to_vec(), kind));
+                    },
+                    _ => {
+                        if name.as_ref() == b"xfrm" && depth == 2 && xfrm.is_none() {
+                            in_xfrm = true;
+                            xfrm = Some([0; 8]);
+                        }
+                        depth += 1;
+                    },
+                }
+            },
+            Ok(Event::Empty(e)) if in_xfrm => {
+                let offset = match e.local_name().as_ref() {
+                    b"off" => 0,
+                    b"ext" => 2,
+                    b"chOff" => 4,
+                    b"chExt" => 6,
+                    _ => continue,
+                };
+                let keys: [&[u8]; 2] = if offset % 4 == 0 {
+                    [b"x", b"y"]
+                } else {
+                    [b"cx", b"cy"]
+                };
+                if let Some(values) = xfrm.as_mut() {
+                    for (index, key) in keys.iter().enumerate() {
+                        values[offset + index] =
+                            attr(&e, key).and_then(|v| v.parse().ok()).unwrap_or(0);
+                    }
+                }
+            },
+            Ok(Event::End(e)) => {
+                if e.local_name().as_ref() == b"xfrm" {
+                    in_xfrm = false;
+                }
+                depth = depth.saturating_sub(1);
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+            _ => {},
+        }
+    }
+
+    Ok((xfrm, children))
+}
+
+/// Parse a DrawingML percentage, in 1/1000ths of a percent or with a `%`
+/// sign, to a fraction.
+fn percentage(value: &str) -> Option<f64> {
+    match value.strip_suffix('%') {
+        Some(percent) => percent.parse::<f64>().ok().map(|p| p / 100.0),
+        None => value.parse::<f64>().ok().map(|p| p / 100_000.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::pptx::Package;
+
+    fn render(path: &str, index: usize, width: u32) -> image::RgbImage {
+        let pkg = Package::open(path).unwrap();
+        let pres = pkg.presentation().unwrap();
+        let png = pres.slides().unwrap()[index].render_to_png(width).unwrap();
+        image::load_from_memory(&png).unwrap().to_rgb8()
+    }
+
+    /// Count pixels in a region that differ from `color`
+    fn count_other(
+        image: &image::RgbImage,
+        (x0, y0, x1, y1): (u32, u32, u32, u32),
+        color: [u8; 3],
+    ) -> usize {
+        (y0..y1)
+            .flat_map(|y| (x0..x1).map(move |x| (x, y)))
+            .filter(|&(x, y)| image.get_pixel(x, y).0 != color)
+            .count()
+    }
+
+    #[test]
+    fn test_render_title_slide() {
+        let image = render("test-data/ooxml/pptx/sample.pptx", 0, 320);
+        assert_eq!(image.dimensions(), (320, 240));
+        assert_eq!(image.get_pixel(2, 2).0, [0xFF, 0xFF, 0xFF]);
+
+        // The title is drawn inside its layout placeholder, and nothing
+        // above it
+        if text::font_available() {
+            let title = (24, 74, 296, 126);
+            assert!(count_other(&image, title, [0xFF; 3]) > 100);
+        }
+        assert_eq!(count_other(&image, (0, 0, 320, 70), [0xFF; 3]), 0);
+    }
+
+    #[test]
+    fn test_render_pictures() {
+        let image = render("test-data/ooxml/pptx/shapes.pptx", 0, 400);
+        let total = count_other(&image, (0, 0, image.width(), image.height()), [0xFF; 3]);
+        assert!(total > 1000, "{}", total);
+    }
+
+    #[test]
+    fn test_render_zero_width() {
+        let pkg = Package::open("test-data/ooxml/pptx/sample.pptx").unwrap();
+        let pres = pkg.presentation().unwrap();
+        assert!(pres.slides().unwrap()[0].render_to_png(0).is_err());
+    }
+
+    #[test]
+    fn test_shape_style() {
+        let xml = br#"<p:sp><p:nvSpPr><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr><p:spPr><a:prstGeom prst="roundRect"><a:avLst><a:gd name="adj" fmla="val 50000"/></a:avLst></a:prstGeom><a:ln w="25400"><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></a:ln></p:spPr><p:txBody><a:bodyPr anchor="ctr" lIns="0" wrap="none"><a:normAutofit fontScale="62500"/></a:bodyPr><a:p><a:r><a:rPr><a:ln w="100"/></a:rPr><a:t>x</a:t></a:r></a:p></p:txBody></p:sp>"#;
+        let style = ShapeStyle::parse(xml).unwrap();
+
+        let frame = Frame {
+            width: 100.0,
+            height: 40.0,
+            ..Frame::default()
+        };
+        assert_eq!(style.geometry(&frame), Geometry::RoundRect { radius: 20.0 });
+        assert_eq!(
+            style.outline(&ColorScheme::default()),
+            Some((RGBColor::new(0xFF, 0, 0), 25_400))
+        );
+        assert_eq!(style.default_font_size(), 44.0);
+        assert_eq!(style.insets[0], 0);
+        assert!(!style.wrap);
+        assert_eq!(style.anchor, Anchor::Middle);
+        assert_eq!(style.font_scale, 0.625);
+    }
+
+    #[test]
+    fn test_group_children_transform() {
+        let xml = br#"<p:grpSp><p:nvGrpSpPr/><p:grpSpPr><a:xfrm><a:off x="1000" y="2000"/><a:ext cx="400" cy="400"/><a:chOff x="0" y="0"/><a:chExt cx="200" cy="100"/></a:xfrm></p:grpSpPr><p:sp><p:spPr><a:xfrm><a:off x="100" y="50"/><a:ext cx="10" cy="10"/></a:xfrm></p:spPr></p:sp><p:pic><p:spPr/></p:pic></p:grpSp>"#;
+        let (xfrm, children) = group_children(xml).unwrap();
+        assert_eq!(xfrm, Some([1000, 2000, 400, 400, 0, 0, 200, 100]));
+        assert_eq!(children.len(), 2);
+        assert_eq!(*children[1].shape_type(), ShapeType::Picture);
+
+        let transform = GroupTransform::IDENTITY.nested(xfrm.unwrap());
+        assert_eq!(transform.map(100.0, 50.0), (1200.0, 2200.0));
+    }
+}
@@ -0,0 +1,404 @@
+//! Text layout for slide rendering.
+//!
+//! Paragraphs are broken into lines greedily at whitespace, and glyphs are
+//! rasterized with a system sans-serif font in the weight and style of each
+//! run. Kerning, shaping and font families are not taken into account.
+
+use super::canvas::{Canvas, Frame, Geometry};
+use crate::common::RGBColor;
+use font_kit::canvas::{Canvas as GlyphCanvas, Format, RasterizationOptions};
+use font_kit::family_name::FamilyName;
+use font_kit::font::Font;
+use font_kit::hinting::HintingOptions;
+use font_kit::properties::{Properties, Style, Weight};
+use font_kit::source::SystemSource;
+use pathfinder_geometry::transform2d::Transform2F;
+use pathfinder_geometry::vector::Vector2F;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// Line height as a multiple of the font size
+const LINE_SPACING: f32 = 1.2;
+/// Baseline offset from the top of a line, as a multiple of the font size
+const ASCENT: f32 = 0.9;
+
+thread_local! {
+    /// Fonts by bold and italic. Finding a system font is slow, so each
+    /// one is looked up once per thread.
+    static FONTS: RefCell<HashMap<(bool, bool), Option<Font>>> = RefCell::new(HashMap::new());
+}
+
+/// Run `f` with the system font for a weight and style, if there is one.
+///
+/// `f` must not call this function again.
+fn with_font<R>(bold: bool, italic: bool, f: impl FnOnce(Option<&Font>) -> R) -> R {
+    FONTS.with(|fonts| {
+        let mut fonts = fonts.borrow_mut();
+        let font = fonts
+            .entry((bold, italic))
+            .or_insert_with(|| load_font(bold, italic));
+        f(font.as_ref())
+    })
+}
+
+fn load_font(bold: bool, italic: bool) -> Option<Font> {
+    let mut properties = Properties::new();
+    if bold {
+        properties.weight = Weight::BOLD;
+    }
+    if italic {
+        properties.style = Style::Italic;
+    }
+    SystemSource::new()
+        .select_best_match(&[FamilyName::SansSerif], &properties)
+        .ok()?
+        .load()
+        .ok()
+}
+
+/// Whether a system font is available to draw text with.
+pub(super) fn font_available() -> bool {
+    with_font(false, false, |font| font.is_some())
+}
+
+/// Horizontal alignment of a paragraph
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum Align {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// Vertical position of the text in its box
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum Anchor {
+    #[default]
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// Text with one set of character properties.
+#[derive(Debug, Clone)]
+pub(super) struct Span {
+    pub text: String,
+    /// Font size in pixels
+    pub size: f32,
+    pub color: RGBColor,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+}
+
+/// A paragraph of spans.
+#[derive(Debug, Clone, Default)]
+pub(super) struct TextParagraph {
+    pub spans: Vec<Span>,
+    pub align: Align,
+    /// Font size in pixels of the paragraph when it has no text
+    pub empty_size: f32,
+}
+
+/// The area text is laid out in, in pixels.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TextArea {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    /// Whether lines wrap at the area width
+    pub wrap: bool,
+    pub anchor: Anchor,
+}
+
+/// A piece of a span placed on a line
+struct Piece<'a> {
+    span: &'a Span,
+    text: &'a str,
+    x: f32,
+    width: f32,
+}
+
+/// A laid out line
+#[derive(Default)]
+struct Line<'a> {
+    pieces: Vec<Piece<'a>>,
+    /// Width up to the end of the last non-whitespace character
+    width: f32,
+    /// Width including trailing whitespace
+    advance: f32,
+    size: f32,
+    align: Align,
+}
+
+impl<'a> Line<'a> {
+    fn push(&mut self, span: &'a Span, text: &'a str, width: f32, trailing_space: f32) {
+        self.pieces.push(Piece {
+            span,
+            text,
+            x: self.advance,
+            width,
+        });
+        if width > trailing_space {
+            self.width = self.advance + width - trailing_space;
+        }
+        self.advance += width;
+        self.size = self.size.max(span.size);
+    }
+}
+
+/// Lay out paragraphs in an area and draw them.
+pub(super) fn draw_text(canvas: &mut Canvas, area: &TextArea, paragraphs: &[TextParagraph]) {
+    if !font_available() {
+        return;
+    }
+    let lines = layout(area, paragraphs);
+    let total: f32 = lines.iter().map(|line| line.size * LINE_SPACING).sum();
+    let mut top = match area.anchor {
+        Anchor::Top => area.y,
+        Anchor::Middle => area.y + (area.height - total) / 2.0,
+        Anchor::Bottom => area.y + area.height - total,
+    };
+
+    for line in &lines {
+        let left = match line.align {
+            Align::Left => area.x,
+            Align::Center => area.x + (area.width - line.width) / 2.0,
+            Align::Right => area.x + area.width - line.width,
+        };
+        let baseline = top + line.size * ASCENT;
+        for piece in &line.pieces {
+            draw_piece(canvas, piece, left + piece.x, baseline);
+        }
+        top += line.size * LINE_SPACING;
+    }
+}
+
+/// Break paragraphs into lines.
+fn layout<'a>(area: &TextArea, paragraphs: &'a [TextParagraph]) -> Vec<Line<'a>> {
+    let mut lines = Vec::new();
+    for paragraph in paragraphs {
+        let new_line = || Line {
+            align: paragraph.align,
+            ..Line::default()
+        };
+        let mut line = new_line();
+        for span in &paragraph.spans {
+            for word in words(&span.text) {
+                if word == "\n" {
+                    if line.size == 0.0 {
+                        line.size = span.size;
+                    }
+                    lines.push(std::mem::replace(&mut line, new_line()));
+                    continue;
+                }
+                let width = measure(span, word);
+                let trailing = word.len() - word.trim_end().len();
+                let trailing_space = measure(span, &word[word.len() - trailing..]);
+                if area.wrap
+                    && !line.pieces.is_empty()
+                    && line.advance + width - trailing_space > area.width
+                {
+                    lines.push(std::mem::replace(&mut line, new_line()));
+                }
+                line.push(span, word, width, trailing_space);
+            }
+        }
+        if line.size == 0.0 {
+            line.size = paragraph.empty_size;
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Split text into words, each with its trailing whitespace, and line
+/// breaks.
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        if let Some(after) = rest.strip_prefix('\n') {
+            let word = &rest[..1];
+            rest = after;
+            return Some(word);
+        }
+        let mut in_space = false;
+        let mut end = rest.len();
+        for (index, ch) in rest.char_indices() {
+            if ch == '\n' {
+                end = index;
+                break;
+            }
+            if ch.is_whitespace() {
+                in_space = true;
+            } else if in_space {
+                end = index;
+                break;
+            }
+        }
+        let (word, after) = rest.split_at(end);
+        rest = after;
+        Some(word)
+    })
+}
+
+/// Width in pixels of text in the font of a span
+fn measure(span: &Span, text: &str) -> f32 {
+    with_font(span.bold, span.italic, |font| match font {
+        Some(font) => {
+            let scale = span.size / font.metrics().units_per_em as f32;
+            text.chars()
+                .filter_map(|ch| glyph(font, ch))
+                .filter_map(|glyph| font.advance(glyph).ok())
+                .map(|advance| advance.x() * scale)
+                .sum()
+        },
+        None => text.chars().count() as f32 * span.size * 0.5,
+    })
+}
+
+fn glyph(font: &Font, ch: char) -> Option<u32> {
+    font.glyph_for_char(ch).or_else(|| {
+        (!ch.is_whitespace())
+            .then(|| font.glyph_for_char('?'))
+            .flatten()
+    })
+}
+
+/// Rasterize the glyphs of a piece with the pen starting at `x` on the
+/// baseline `y`, then draw its underline.
+fn draw_piece(canvas: &mut Canvas, piece: &Piece<'_>, x: f32, y: f32) {
+    let span = piece.span;
+    with_font(span.bold, span.italic, |font| {
+        let Some(font) = font else {
+            return;
+        };
+        let scale = span.size / font.metrics().units_per_em as f32;
+        let mut pen = x;
+        for ch in piece.text.chars() {
+            let Some(glyph) = glyph(font, ch) else {
+                continue;
+            };
+            if !ch.is_whitespace() {
+                draw_glyph(canvas, font, glyph, span, (pen, y));
+            }
+            pen += font.advance(glyph).map(|a| a.x() * scale).unwrap_or(0.0);
+        }
+    });
+
+    if span.underline {
+        let thickness = (span.size / 16.0).max(1.0);
+        let frame = Frame {
+            x,
+            y: y + span.size * 0.1,
+            width: piece.width,
+            height: 0.0,
+            ..Frame::default()
+        };
+        canvas.stroke(&frame, Geometry::Line, span.color, thickness);
+    }
+}
+
+/// Rasterize one glyph with its origin at `(x, y)`, keeping the fraction
+/// of the pen position for even spacing.
+fn draw_glyph(canvas: &mut Canvas, font: &Font, glyph: u32, span: &Span, (x, y): (f32, f32)) {
+    let offset = Vector2F::new(x.fract(), y.fract());
+    let options = RasterizationOptions::GrayscaleAa;
+    let Ok(bounds) = font.raster_bounds(
+        glyph,
+        span.size,
+        Transform2F::from_translation(offset),
+        HintingOptions::None,
+        options,
+    ) else {
+        return;
+    };
+    if bounds.width() <= 0 || bounds.height() <= 0 {
+        return;
+    }
+
+    let mut mask = GlyphCanvas::new(bounds.size(), Format::A8);
+    let transform = Transform2F::from_translation(offset - bounds.origin().to_f32());
+    if font
+        .rasterize_glyph(
+            &mut mask,
+            glyph,
+            span.size,
+            transform,
+            HintingOptions::None,
+            options,
+        )
+        .is_err()
+    {
+        return;
+    }
+    canvas.draw_mask(
+        (
+            x.floor() as i32 + bounds.origin_x(),
+            y.floor() as i32 + bounds.origin_y(),
+        ),
+        (bounds.width() as usize, bounds.height() as usize),
+        mask.stride,
+        &mask.pixels,
+        span.color,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str) -> Span {
+        Span {
+            text: text.to_string(),
+            size: 20.0,
+            color: RGBColor::new(0, 0, 0),
+            bold: false,
+            italic: false,
+            underline: false,
+        }
+    }
+
+    #[test]
+    fn test_words_keep_trailing_whitespace() {
+        let words: Vec<_> = words("one  two\nthree ").collect();
+        assert_eq!(words, ["one  ", "two", "\n", "three "]);
+    }
+
+    #[test]
+    fn test_layout_wraps_at_area_width() {
+        let paragraphs = [TextParagraph {
+            spans: vec![span("alpha beta gamma delta")],
+            align: Align::Center,
+            empty_size: 20.0,
+        }];
+        let three_words = measure(&paragraphs[0].spans[0], "alpha beta gamma");
+        let area = TextArea {
+            x: 0.0,
+            y: 0.0,
+            width: three_words + 1.0,
+            height: 100.0,
+            wrap: true,
+            anchor: Anchor::Top,
+        };
+
+        let lines = layout(&area, &paragraphs);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].pieces.len(), 3);
+        assert!(lines[0].width <= area.width);
+        assert_eq!(lines[1].align, Align::Center);
+
+        let unwrapped = layout(
+            &TextArea {
+                wrap: false,
+                ..area
+            },
+            &paragraphs,
+        );
+        assert_eq!(unwrapped.len(), 1);
+    }
+}
@@ -1,5 +1,8 @@
 /// Base shape types for PowerPoint presentations.
+use crate::common::{RGBColor, Rect};
+use crate::ooxml::drawings::color::{ColorScheme, ColorSpec, read_fill_color};
 use crate::ooxml::error::Result;
+use crate::ooxml::pptx::shapes::table::Table;
 use crate::ooxml::pptx::shapes::textframe::TextFrame;
 use quick_xml::Reader;
 use quick_xml::events::Event;
@@ -49,8 +52,8 @@ pub struct BaseShape {
     geometry: Option<ShapeGeometry>,
 }
 
-/// Shape geometry (position and size).
-#[derive(Debug, Clone, Copy)]
+/// Shape geometry (position, size, rotation and flips).
+#[derive(Debug, Clone, Copy, Default)]
 struct ShapeGeometry {
     /// X position in EMUs
     x: i64,
@@ -60,6 +63,14 @@ struct ShapeGeometry {
     cx: i64,
     /// Height in EMUs
     cy: i64,
+    /// Clockwise rotation in 60000ths of a degree
+    rot: i64,
+    /// Horizontal flip
+    flip_h: bool,
+    /// Vertical flip
+    flip_v: bool,
+    /// Whether the shape has its own `xfrm` element
+    has_xfrm: bool,
 }
 
 impl BaseShape {
@@ -137,6 +148,39 @@ impl BaseShape {
         Ok(self.geometry.unwrap().cy)
     }
 
+    /// Get the bounding box of the shape on the slide.
+    ///
+    /// Read from the `a:off` and `a:ext` of the shape's transform. The box is
+    /// the unrotated frame of the shape; combine it with
+    /// [`rotation`](Self::rotation) and the flips to place a rotated shape.
+    /// Returns `None` for shapes without their own transform, such as
+    /// placeholders inheriting their position from the layout.
+    pub fn bounds(&mut self) -> Result<Option<Rect>> {
+        self.ensure_geometry()?;
+        let geometry = self.geometry.unwrap();
+        Ok(geometry
+            .has_xfrm
+            .then(|| Rect::from_emus(geometry.x, geometry.y, geometry.cx, geometry.cy)))
+    }
+
+    /// Get the clockwise rotation in degrees.
+    pub fn rotation(&mut self) -> Result<f64> {
+        self.ensure_geometry()?;
+        Ok(self.geometry.unwrap().rot as f64 / 60000.0)
+    }
+
+    /// Check whether the shape is flipped horizontally.
+    pub fn flip_horizontal(&mut self) -> Result<bool> {
+        self.ensure_geometry()?;
+        Ok(self.geometry.unwrap().flip_h)
+    }
+
+    /// Check whether the shape is flipped vertically.
+    pub fn flip_vertical(&mut self) -> Result<bool> {
+        self.ensure_geometry()?;
+        Ok(self.geometry.unwrap().flip_v)
+    }
+
     /// Check if this shape is a placeholder.
     pub fn is_placeholder(&self) -> bool {
         // Look for <p:ph> element
@@ -212,6 +256,99 @@ impl BaseShape {
         self.shape_type == ShapeType::GraphicFrame && self.contains_table_marker()
     }
 
+    /// Get the table held by this shape.
+    ///
+    /// Returns None if the shape is not a graphic frame containing a table.
+    pub fn table(&self) -> Result<Option<Table>> {
+        if !self.has_table() {
+            return Ok(None);
+        }
+        Table::from_graphic_frame_xml(&self.xml_bytes).map(Some)
+    }
+
+    /// Get the fill color of this shape as written.
+    ///
+    /// Reads the solid fill of the shape properties (`p:spPr`), falling back
+    /// to the fill reference of the shape style (`p:style/a:fillRef`).
+    /// Returns None if the shape has no fill or a non-solid fill.
+    pub fn fill_color_spec(&self) -> Result<Option<ColorSpec>> {
+        let mut reader = Reader::from_reader(&self.xml_bytes[..]);
+        reader.config_mut().trim_text(true);
+
+        let mut in_sp_pr = false;
+        let mut after_sp_pr = false;
+        let mut in_ln = false;
+        let mut in_style = false;
+
+        loop {
+            let event = reader.read_event();
+            let is_empty = matches!(event, Ok(Event::Empty(_)));
+            match event {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    let name = e.local_name();
+                    match name.as_ref() {
+                        b"spPr" | b"grpSpPr" if !after_sp_pr => {
+                            if is_empty {
+                                after_sp_pr = true;
+                            } else {
+                                in_sp_pr = true;
+                            }
+                        },
+                        b"ln" if in_sp_pr => in_ln = !is_empty,
+                        b"solidFill" if in_sp_pr && !in_ln && !is_empty => {
+                            return read_fill_color(&mut reader, b"solidFill");
+                        },
+                        b"noFill" | b"solidFill" | b"gradFill" | b"blipFill" | b"pattFill"
+                        | b"grpFill"
+                            if in_sp_pr && !in_ln =>
+                        {
+                            return Ok(None);
+                        },
+                        b"style" if after_sp_pr => in_style = !is_empty,
+                        b"fillRef" if in_style && !is_empty => {
+                            let idx = e
+                                .attributes()
+                                .flatten()
+                                .find(|attr| attr.key.as_ref() == b"idx")
+                                .map(|attr| attr.value.into_owned());
+                            // Index 0 means no background fill
+                            if idx.as_deref() == Some(b"0") {
+                                return Ok(None);
+                            }
+                            return read_fill_color(&mut reader, b"fillRef");
+                        },
+                        // Anything else after the shape properties is not
+                        // part of the shape style
+                        _ if after_sp_pr && !in_style => return Ok(None),
+                        _ => {},
+                    }
+                },
+                Ok(Event::End(ref e)) => match e.local_name().as_ref() {
+                    b"ln" => in_ln = false,
+                    b"spPr" | b"grpSpPr" if in_sp_pr => {
+                        in_sp_pr = false;
+                        after_sp_pr = true;
+                    },
+                    b"style" if in_style => return Ok(None),
+                    _ => {},
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(crate::ooxml::error::OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Get the fill color of this shape, resolving theme colors against
+    /// `scheme` (see [`Theme::color_scheme`](crate::ooxml::pptx::parts::Theme::color_scheme)).
+    pub fn fill_color(&self, scheme: &ColorScheme) -> Result<Option<RGBColor>> {
+        Ok(self
+            .fill_color_spec()?
+            .and_then(|spec| spec.resolve(scheme)))
+    }
+
     /// Internal helper to check for table marker in XML.
     fn contains_table_marker(&self) -> bool {
         let xml_str = String::from_utf8_lossy(&self.xml_bytes);
@@ -219,6 +356,9 @@ impl BaseShape {
     }
 
     /// Ensure geometry is parsed and cached.
+    ///
+    /// Only the first `xfrm` element belongs to the shape itself; the
+    /// transforms of a group's children come after it.
     fn ensure_geometry(&mut self) -> Result<()> {
         if self.geometry.is_some() {
             return Ok(());
@@ -227,61 +367,59 @@ impl BaseShape {
         let mut reader = Reader::from_reader(&self.xml_bytes[..]);
         reader.config_mut().trim_text(true);
 
-        let mut x = 0;
-        let mut y = 0;
-        let mut cx = 0;
-        let mut cy = 0;
+        let mut geometry = ShapeGeometry::default();
+        let mut in_xfrm = false;
 
         loop {
-            match reader.read_event() {
+            let event = reader.read_event();
+            let is_empty = matches!(event, Ok(Event::Empty(_)));
+            match event {
                 Ok(Event::Empty(ref e)) | Ok(Event::Start(ref e)) => {
-                    let tag_name = e.local_name();
-
-                    if tag_name.as_ref() == b"off" {
-                        for attr in e.attributes().flatten() {
-                            match attr.key.as_ref() {
-                                b"x" => {
-                                    x = std::str::from_utf8(&attr.value)
-                                        .ok()
-                                        .and_then(|s| s.parse().ok())
-                                        .unwrap_or(0);
-                                },
-                                b"y" => {
-                                    y = std::str::from_utf8(&attr.value)
-                                        .ok()
-                                        .and_then(|s| s.parse().ok())
-                                        .unwrap_or(0);
-                                },
-                                _ => {},
+                    match e.local_name().as_ref() {
+                        b"xfrm" if !geometry.has_xfrm => {
+                            geometry.has_xfrm = true;
+                            in_xfrm = !is_empty;
+                            for attr in e.attributes().flatten() {
+                                let value = std::str::from_utf8(&attr.value).unwrap_or("");
+                                match attr.key.as_ref() {
+                                    b"rot" => geometry.rot = value.parse().unwrap_or(0),
+                                    b"flipH" => geometry.flip_h = matches!(value, "1" | "true"),
+                                    b"flipV" => geometry.flip_v = matches!(value, "1" | "true"),
+                                    _ => {},
+                                }
                             }
-                        }
-                    } else if tag_name.as_ref() == b"ext" {
-                        for attr in e.attributes().flatten() {
-                            match attr.key.as_ref() {
-                                b"cx" => {
-                                    cx = std::str::from_utf8(&attr.value)
-                                        .ok()
-                                        .and_then(|s| s.parse().ok())
-                                        .unwrap_or(0);
-                                },
-                                b"cy" => {
-                                    cy = std::str::from_utf8(&attr.value)
-                                        .ok()
-                                        .and_then(|s| s.parse().ok())
-                                        .unwrap_or(0);
-                                },
-                                _ => {},
+                        },
+                        b"off" if in_xfrm => {
+                            for attr in e.attributes().flatten() {
+                                let value = std::str::from_utf8(&attr.value).unwrap_or("");
+                                match attr.key.as_ref() {
+                                    b"x" => geometry.x = value.parse().unwrap_or(0),
+                                    b"y" => geometry.y = value.parse().unwrap_or(0),
+                                    _ => {},
+                                }
                             }
-                        }
+                        },
+                        b"ext" if in_xfrm => {
+                            for attr in e.attributes().flatten() {
+                                let value = std::str::from_utf8(&attr.value).unwrap_or("");
+                                match attr.key.as_ref() {
+                                    b"cx" => geometry.cx = value.parse().unwrap_or(0),
+                                    b"cy" => geometry.cy = value.parse().unwrap_or(0),
+                                    _ => {},
+                                }
+                            }
+                        },
+                        _ => {},
                     }
                 },
+                Ok(Event::End(ref e)) if in_xfrm && e.local_name().as_ref() == b"xfrm" => break,
                 Ok(Event::Eof) => break,
                 Err(_) => break,
                 _ => {},
             }
         }
 
-        self.geometry = Some(ShapeGeometry { x, y, cx, cy });
+        self.geometry = Some(geometry);
         Ok(())
     }
 
@@ -346,3 +484,80 @@ impl Shape {
         tf.text()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shape_bounds_rotation_and_flips() {
+        let xml = br#"<p:sp><p:nvSpPr><p:cNvPr id="2" name="Arrow"/></p:nvSpPr>
+            <p:spPr><a:xfrm rot="5400000" flipH="1"><a:off x="914400" y="457200"/><a:ext cx="1828800" cy="914400"/></a:xfrm></p:spPr>
+        </p:sp>"#;
+        let mut shape = BaseShape::new(xml.to_vec(), ShapeType::Shape);
+        assert_eq!(
+            shape.bounds().unwrap(),
+            Some(Rect::from_emus(914400, 457200, 1828800, 914400))
+        );
+        assert_eq!(shape.rotation().unwrap(), 90.0);
+        assert!(shape.flip_horizontal().unwrap());
+        assert!(!shape.flip_vertical().unwrap());
+
+        // Child transforms of a group do not affect the group's own bounds
+        let xml = br#"<p:grpSp><p:grpSpPr><a:xfrm><a:off x="100" y="200"/><a:ext cx="300" cy="400"/>
+            <a:chOff x="0" y="0"/><a:chExt cx="300" cy="400"/></a:xfrm></p:grpSpPr>
+            <p:sp><p:spPr><a:xfrm><a:off x="5" y="6"/><a:ext cx="7" cy="8"/></a:xfrm></p:spPr></p:sp>
+        </p:grpSp>"#;
+        let mut group = BaseShape::new(xml.to_vec(), ShapeType::GroupShape);
+        assert_eq!(
+            group.bounds().unwrap(),
+            Some(Rect::from_emus(100, 200, 300, 400))
+        );
+        assert_eq!(group.rotation().unwrap(), 0.0);
+
+        // Placeholders without a transform inherit their position
+        let xml =
+            br#"<p:sp><p:nvSpPr><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr><p:spPr/></p:sp>"#;
+        let mut placeholder = BaseShape::new(xml.to_vec(), ShapeType::Shape);
+        assert_eq!(placeholder.bounds().unwrap(), None);
+    }
+
+    #[test]
+    fn test_shape_fill_color() {
+        use crate::ooxml::drawings::color::{ColorTransform, ThemeColorSlot};
+        let scheme = ColorScheme::default();
+
+        let xml = br#"<p:sp><p:spPr><a:solidFill><a:schemeClr val="accent2"><a:lumMod val="75000"/></a:schemeClr></a:solidFill>
+            <a:ln><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></a:ln></p:spPr></p:sp>"#;
+        let shape = BaseShape::new(xml.to_vec(), ShapeType::Shape);
+        assert_eq!(
+            shape.fill_color_spec().unwrap(),
+            Some(ColorSpec::Theme {
+                slot: ThemeColorSlot::Accent2,
+                transforms: vec![ColorTransform::LumMod(0.75)],
+            })
+        );
+        assert_eq!(
+            shape.fill_color(&scheme).unwrap(),
+            Some(RGBColor::new(0xC5, 0x5A, 0x11))
+        );
+
+        // The line color is not the fill
+        let xml = br#"<p:sp><p:spPr><a:ln><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></a:ln></p:spPr></p:sp>"#;
+        let shape = BaseShape::new(xml.to_vec(), ShapeType::Shape);
+        assert_eq!(shape.fill_color_spec().unwrap(), None);
+
+        // Fill taken from the shape style
+        let xml = br#"<p:sp><p:spPr><a:prstGeom prst="rect"/></p:spPr><p:style><a:lnRef idx="2"><a:schemeClr val="accent1"/></a:lnRef>
+            <a:fillRef idx="1"><a:schemeClr val="accent1"/></a:fillRef></p:style></p:sp>"#;
+        let shape = BaseShape::new(xml.to_vec(), ShapeType::Shape);
+        assert_eq!(
+            shape.fill_color(&scheme).unwrap(),
+            Some(RGBColor::new(0x44, 0x72, 0xC4))
+        );
+
+        let xml = br#"<p:sp><p:spPr><a:noFill/></p:spPr><p:style><a:fillRef idx="1"><a:schemeClr val="accent1"/></a:fillRef></p:style></p:sp>"#;
+        let shape = BaseShape::new(xml.to_vec(), ShapeType::Shape);
+        assert_eq!(shape.fill_color_spec().unwrap(), None);
+    }
+}
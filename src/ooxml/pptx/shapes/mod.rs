@@ -15,4 +15,4 @@ pub mod textframe;
 pub use base::{BaseShape, Shape, ShapeType};
 pub use picture::Picture;
 pub use table::{Table, TableCell, TableRow};
-pub use textframe::TextFrame;
+pub use textframe::{TextFrame, TextRun};
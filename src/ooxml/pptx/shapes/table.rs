@@ -39,7 +39,6 @@ impl Table {
     /// to extract just the table portion.
     pub fn from_graphic_frame_xml(xml_bytes: &[u8]) -> Result<Self> {
         let mut reader = Reader::from_reader(xml_bytes);
-        reader.config_mut().trim_text(true);
 
         let mut table_xml = Vec::new();
         let mut in_table = false;
@@ -82,6 +81,11 @@ impl Table {
                 Ok(Event::Text(e)) if in_table => {
                     table_xml.extend_from_slice(e.as_ref());
                 },
+                Ok(Event::GeneralRef(e)) if in_table => {
+                    table_xml.push(b'&');
+                    table_xml.extend_from_slice(e.as_ref());
+                    table_xml.push(b';');
+                },
                 Ok(Event::Empty(e)) if in_table => {
                     table_xml.push(b'<');
                     table_xml.extend_from_slice(e.name().as_ref());
@@ -144,7 +148,6 @@ impl Table {
     /// Get all rows in the table.
     pub fn rows(&self) -> Result<Vec<TableRow>> {
         let mut reader = Reader::from_reader(&self.xml_bytes[..]);
-        reader.config_mut().trim_text(true);
 
         let mut rows = Vec::new();
         let mut current_row_xml = Vec::new();
@@ -189,6 +192,11 @@ impl Table {
                 Ok(Event::Text(e)) if in_row => {
                     current_row_xml.extend_from_slice(e.as_ref());
                 },
+                Ok(Event::GeneralRef(e)) if in_row => {
+                    current_row_xml.push(b'&');
+                    current_row_xml.extend_from_slice(e.as_ref());
+                    current_row_xml.push(b';');
+                },
                 Ok(Event::Empty(e)) if in_row => {
                     current_row_xml.push(b'<');
                     current_row_xml.extend_from_slice(e.name().as_ref());
@@ -264,7 +272,6 @@ impl TableRow {
     /// Get all cells in this row.
     pub fn cells(&self) -> Result<Vec<TableCell>> {
         let mut reader = Reader::from_reader(&self.xml_bytes[..]);
-        reader.config_mut().trim_text(true);
 
         let mut cells = Vec::new();
         let mut current_cell_xml = Vec::new();
@@ -278,7 +285,16 @@ impl TableRow {
                         in_cell = true;
                         depth = 1;
                         current_cell_xml.clear();
-                        current_cell_xml.extend_from_slice(b"<a:tc>");
+                        // Keep the span and merge attributes of the cell
+                        current_cell_xml.extend_from_slice(b"<a:tc");
+                        for attr in e.attributes().flatten() {
+                            current_cell_xml.push(b' ');
+                            current_cell_xml.extend_from_slice(attr.key.as_ref());
+                            current_cell_xml.extend_from_slice(b"=\"");
+                            current_cell_xml.extend_from_slice(&attr.value);
+                            current_cell_xml.push(b'"');
+                        }
+                        current_cell_xml.push(b'>');
                     } else if in_cell {
                         depth += 1;
                         current_cell_xml.push(b'<');
@@ -309,6 +325,11 @@ impl TableRow {
                 Ok(Event::Text(e)) if in_cell => {
                     current_cell_xml.extend_from_slice(e.as_ref());
                 },
+                Ok(Event::GeneralRef(e)) if in_cell => {
+                    current_cell_xml.push(b'&');
+                    current_cell_xml.extend_from_slice(e.as_ref());
+                    current_cell_xml.push(b';');
+                },
                 Ok(Event::Empty(e)) if in_cell => {
                     current_cell_xml.push(b'<');
                     current_cell_xml.extend_from_slice(e.name().as_ref());
@@ -344,10 +365,62 @@ impl TableCell {
         Self { xml_bytes }
     }
 
+    /// Get the number of columns spanned by this cell (1 if not merged).
+    pub fn grid_span(&self) -> Result<u32> {
+        Ok(self
+            .attribute(b"gridSpan")?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1))
+    }
+
+    /// Get the number of rows spanned by this cell (1 if not merged).
+    pub fn row_span(&self) -> Result<u32> {
+        Ok(self
+            .attribute(b"rowSpan")?
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(1))
+    }
+
+    /// Check if this cell is merged into the cell to its left.
+    pub fn is_h_merge(&self) -> Result<bool> {
+        Ok(self
+            .attribute(b"hMerge")?
+            .is_some_and(|v| v == "1" || v == "true"))
+    }
+
+    /// Check if this cell is merged into the cell above.
+    pub fn is_v_merge(&self) -> Result<bool> {
+        Ok(self
+            .attribute(b"vMerge")?
+            .is_some_and(|v| v == "1" || v == "true"))
+    }
+
+    /// Get an attribute of the `a:tc` element.
+    fn attribute(&self, name: &[u8]) -> Result<Option<String>> {
+        let mut reader = Reader::from_reader(&self.xml_bytes[..]);
+        reader.config_mut().trim_text(true);
+
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => {
+                    return Ok(e
+                        .attributes()
+                        .flatten()
+                        .find(|attr| attr.key.as_ref() == name)
+                        .map(|attr| String::from_utf8_lossy(&attr.value).into_owned()));
+                },
+                Ok(Event::Eof) => return Ok(None),
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+    }
+
     /// Extract all text from this cell.
+    ///
+    /// The text of separate runs is joined with spaces.
     pub fn text(&self) -> Result<String> {
         let mut reader = Reader::from_reader(&self.xml_bytes[..]);
-        reader.config_mut().trim_text(true);
 
         let mut text = String::new();
         let mut in_text_element = false;
@@ -357,16 +430,31 @@ impl TableCell {
                 Ok(Event::Start(e)) => {
                     if e.local_name().as_ref() == b"t" {
                         in_text_element = true;
+                        if !text.is_empty() && !text.ends_with(' ') {
+                            text.push(' ');
+                        }
                     }
                 },
                 Ok(Event::Text(e)) if in_text_element => {
                     let t = std::str::from_utf8(e.as_ref())
                         .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                    if !text.is_empty() && !text.ends_with(' ') {
-                        text.push(' ');
-                    }
                     text.push_str(t);
                 },
+                Ok(Event::GeneralRef(e)) if in_text_element => {
+                    // Entity references are reported apart from the surrounding text
+                    if let Some(ch) = e
+                        .resolve_char_ref()
+                        .map_err(|e| OoxmlError::Xml(e.to_string()))?
+                    {
+                        text.push(ch);
+                    } else {
+                        let name = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                        if let Some(resolved) = quick_xml::escape::resolve_predefined_entity(&name)
+                        {
+                            text.push_str(resolved);
+                        }
+                    }
+                },
                 Ok(Event::End(e)) => {
                     if e.local_name().as_ref() == b"t" {
                         in_text_element = false;
@@ -381,3 +469,29 @@ impl TableCell {
         Ok(text.trim().to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_spans() {
+        let xml = br#"<p:graphicFrame><a:graphic><a:graphicData><a:tbl><a:tblGrid/>
+            <a:tr h="100">
+                <a:tc gridSpan="2"><a:txBody><a:p><a:r><a:t>Q1 &amp; Q2</a:t></a:r></a:p></a:txBody></a:tc>
+                <a:tc hMerge="1"><a:txBody><a:p/></a:txBody></a:tc>
+            </a:tr>
+        </a:tbl></a:graphicData></a:graphic></p:graphicFrame>"#;
+
+        let table = Table::from_graphic_frame_xml(xml).unwrap();
+        let anchor = table.cell(0, 0).unwrap().unwrap();
+        assert_eq!(anchor.text().unwrap(), "Q1 & Q2");
+        assert_eq!(anchor.grid_span().unwrap(), 2);
+        assert_eq!(anchor.row_span().unwrap(), 1);
+        assert!(!anchor.is_h_merge().unwrap());
+
+        let covered = table.cell(0, 1).unwrap().unwrap();
+        assert!(covered.is_h_merge().unwrap());
+        assert!(!covered.is_v_merge().unwrap());
+    }
+}
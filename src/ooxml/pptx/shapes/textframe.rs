@@ -1,7 +1,9 @@
 /// Text frame for accessing text content in shapes.
+use crate::ooxml::docx::content_control::push_general_ref;
+use crate::ooxml::drawings::color::{ColorSpec, read_fill_color};
 use crate::ooxml::error::{OoxmlError, Result};
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
 
 /// A text frame containing text content.
 ///
@@ -80,10 +82,11 @@ impl TextFrame {
 
     /// Get paragraphs in this text frame.
     ///
-    /// Returns a vector of Paragraph objects.
+    /// Returns a vector of Paragraph objects. Whitespace in the text is
+    /// kept, so the runs of a paragraph join up into its text.
     pub fn paragraphs(&self) -> Result<Vec<Paragraph>> {
         let mut reader = Reader::from_reader(&self.xml_bytes[..]);
-        reader.config_mut().trim_text(true);
+        reader.config_mut().trim_text(false);
 
         let mut paragraphs = Vec::new();
         let mut current_para_xml = Vec::new();
@@ -129,6 +132,11 @@ impl TextFrame {
                 Ok(Event::Text(e)) if in_para => {
                     current_para_xml.extend_from_slice(e.as_ref());
                 },
+                Ok(Event::GeneralRef(e)) if in_para => {
+                    current_para_xml.push(b'&');
+                    current_para_xml.extend_from_slice(e.as_ref());
+                    current_para_xml.push(b';');
+                },
                 Ok(Event::Empty(e)) if in_para => {
                     current_para_xml.push(b'<');
                     current_para_xml.extend_from_slice(e.name().as_ref());
@@ -218,4 +226,183 @@ impl Paragraph {
 
         Ok(text)
     }
+
+    /// Get the runs of this paragraph with their character properties.
+    ///
+    /// Text fields (`a:fld`), such as slide numbers, are returned as runs
+    /// with their current text. A line break (`a:br`) is a run with the
+    /// text `"\n"`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,ignore
+    /// for run in paragraph.runs()? {
+    ///     println!("{} (bold: {:?}, size: {:?})", run.text, run.bold, run.font_size);
+    /// }
+    /// ```
+    pub fn runs(&self) -> Result<Vec<TextRun>> {
+        let mut reader = Reader::from_reader(&self.xml_bytes[..]);
+
+        let mut runs = Vec::new();
+        let mut current: Option<TextRun> = None;
+        let mut in_rpr = false;
+        let mut in_ln = false;
+        let mut in_text = false;
+
+        loop {
+            let event = reader.read_event();
+            let is_empty = matches!(event, Ok(Event::Empty(_)));
+            match event {
+                Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => {
+                    match e.local_name().as_ref() {
+                        b"r" | b"fld" if !is_empty => current = Some(TextRun::default()),
+                        b"br" => runs.push(TextRun {
+                            text: "\n".to_string(),
+                            ..TextRun::default()
+                        }),
+                        b"rPr" => {
+                            if let Some(run) = current.as_mut() {
+                                run.read_properties(e);
+                                in_rpr = !is_empty;
+                            }
+                        },
+                        b"ln" if in_rpr => in_ln = !is_empty,
+                        b"solidFill" if in_rpr && !in_ln && !is_empty => {
+                            let color = read_fill_color(&mut reader, b"solidFill")?;
+                            if let Some(run) = current.as_mut() {
+                                run.color = color;
+                            }
+                        },
+                        b"t" if current.is_some() => in_text = !is_empty,
+                        _ => {},
+                    }
+                },
+                Ok(Event::Text(e)) if in_text => {
+                    let text = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    if let Some(run) = current.as_mut() {
+                        run.text.push_str(&text);
+                    }
+                },
+                Ok(Event::GeneralRef(e)) if in_text => {
+                    if let Some(run) = current.as_mut() {
+                        push_general_ref(&mut run.text, &e)?;
+                    }
+                },
+                Ok(Event::End(e)) => match e.local_name().as_ref() {
+                    b"r" | b"fld" => runs.extend(current.take()),
+                    b"rPr" => in_rpr = false,
+                    b"ln" => in_ln = false,
+                    b"t" => in_text = false,
+                    _ => {},
+                },
+                Ok(Event::Eof) => break,
+                Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+                _ => {},
+            }
+        }
+
+        Ok(runs)
+    }
+
+    /// Get the horizontal alignment set on the paragraph (`a:pPr/@algn`),
+    /// such as `"l"`, `"ctr"`, `"r"` or `"just"`.
+    ///
+    /// Returns `None` when the alignment is inherited.
+    pub fn alignment(&self) -> Option<String> {
+        let mut reader = Reader::from_reader(&self.xml_bytes[..]);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    b"pPr" => {
+                        return e
+                            .attributes()
+                            .flatten()
+                            .find(|attr| attr.key.as_ref() == b"algn")
+                            .map(|attr| String::from_utf8_lossy(&attr.value).into_owned());
+                    },
+                    // Paragraph properties come before the runs
+                    b"r" | b"fld" | b"br" | b"endParaRPr" => return None,
+                    _ => {},
+                },
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => {},
+            }
+        }
+    }
+}
+
+/// A run of text in a paragraph with its character properties (`a:rPr`).
+///
+/// Properties are `None` when the run does not set them, in which case they
+/// are inherited from the paragraph, the layout or the master.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TextRun {
+    /// Text of the run
+    pub text: String,
+    /// Font size in points
+    pub font_size: Option<f64>,
+    /// Bold formatting
+    pub bold: Option<bool>,
+    /// Italic formatting
+    pub italic: Option<bool>,
+    /// Underline formatting (any underline style other than `none`)
+    pub underline: Option<bool>,
+    /// Solid fill color of the text
+    pub color: Option<ColorSpec>,
+}
+
+impl TextRun {
+    /// Read the attributes of an `a:rPr` element.
+    fn read_properties(&mut self, e: &BytesStart<'_>) {
+        for attr in e.attributes().flatten() {
+            let value = std::str::from_utf8(&attr.value).unwrap_or("");
+            match attr.key.as_ref() {
+                // Hundredths of a point
+                b"sz" => self.font_size = value.parse::<f64>().ok().map(|sz| sz / 100.0),
+                b"b" => self.bold = Some(matches!(value, "1" | "true")),
+                b"i" => self.italic = Some(matches!(value, "1" | "true")),
+                b"u" => self.underline = Some(value != "none"),
+                _ => {},
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::RGBColor;
+
+    #[test]
+    fn test_paragraph_runs_with_properties() {
+        let xml = br#"<p:sp><p:txBody><a:bodyPr/><a:p><a:r><a:rPr lang="en-US" sz="2400" b="1"><a:ln><a:solidFill><a:srgbClr val="00FF00"/></a:solidFill></a:ln><a:solidFill><a:srgbClr val="FF0000"/></a:solidFill></a:rPr><a:t>Q&amp;A </a:t></a:r><a:br/><a:r><a:rPr u="sng" i="0"/><a:t>plain</a:t></a:r><a:fld id="{1}" type="slidenum"><a:t>3</a:t></a:fld><a:endParaRPr sz="1800"/></a:p></p:txBody></p:sp>"#;
+        let frame = TextFrame::from_xml(xml).unwrap();
+        let paragraphs = frame.paragraphs().unwrap();
+        assert_eq!(paragraphs.len(), 1);
+
+        let runs = paragraphs[0].runs().unwrap();
+        assert_eq!(runs.len(), 4);
+        assert_eq!(runs[0].text, "Q&A ");
+        assert_eq!(runs[0].font_size, Some(24.0));
+        assert_eq!(runs[0].bold, Some(true));
+        assert_eq!(
+            runs[0].color,
+            Some(ColorSpec::Rgb(RGBColor::new(0xFF, 0, 0)))
+        );
+        assert_eq!(runs[1].text, "\n");
+        assert_eq!(runs[2].text, "plain");
+        assert_eq!(runs[2].underline, Some(true));
+        assert_eq!(runs[2].italic, Some(false));
+        assert_eq!(runs[2].bold, None);
+        assert_eq!(runs[3].text, "3");
+        assert_eq!(paragraphs[0].alignment(), None);
+    }
+
+    #[test]
+    fn test_paragraph_alignment() {
+        let paragraph = Paragraph::new(
+            br#"<a:p><a:pPr algn="ctr" lvl="1"/><a:r><a:t>Centered</a:t></a:r></a:p>"#.to_vec(),
+        );
+        assert_eq!(paragraph.alignment().as_deref(), Some("ctr"));
+    }
 }
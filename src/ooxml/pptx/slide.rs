@@ -1,8 +1,19 @@
 /// Slide-related objects, including Slide, SlideLayout, and SlideMaster.
+use crate::common::Rect;
+use crate::common::walk::TextEvent;
+use crate::ooxml::charts::Chart;
+use crate::ooxml::charts::reader::read_related_charts;
 use crate::ooxml::error::Result;
+use crate::ooxml::opc::Part;
+use crate::ooxml::opc::constants::relationship_type as rt;
 use crate::ooxml::opc::packuri::PackURI;
 use crate::ooxml::pptx::parts::{SlideLayoutPart, SlideMasterPart, SlidePart};
 use crate::ooxml::pptx::shapes::base::BaseShape;
+use crate::ooxml::pptx::shapes::table::Table;
+use crate::ooxml::pptx::smartart::{SmartArt, diagram_rel_ids, read_related_smartart};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::ops::ControlFlow;
 
 /// A slide in a presentation.
 ///
@@ -101,7 +112,67 @@ impl<'a> Slide<'a> {
     /// # Ok::<(), Box<dyn std::error::Error>>(())
     /// ```
     pub fn text(&self) -> Result<String> {
-        self.part.extract_text()
+        self.part
+            .extract_text_with_diagrams(&|rel_id| self.smartart_text(rel_id))
+    }
+
+    /// Report the text of this slide to `visitor` while parsing the slide
+    /// part, stopping as soon as it returns [`ControlFlow::Break`].
+    ///
+    /// See [`crate::Presentation::walk_text`].
+    pub fn walk_text(
+        &self,
+        visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        self.part
+            .walk_text(&|rel_id| self.smartart_text(rel_id), visitor)
+    }
+
+    /// Get the title of this slide.
+    ///
+    /// The title is the text of the shape holding the `title` or `ctrTitle`
+    /// placeholder. Returns `None` if the slide has no title placeholder.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::pptx::Package;
+    ///
+    /// let pkg = Package::open("presentation.pptx")?;
+    /// let pres = pkg.presentation()?;
+    ///
+    /// for slide in pres.slides()?.iter() {
+    ///     if let Some(title) = slide.title()? {
+    ///         println!("Title: {}", title);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn title(&self) -> Result<Option<String>> {
+        for shape in self.shapes()? {
+            if shape.is_placeholder()
+                && matches!(shape.placeholder_type()?.as_str(), "title" | "ctrTitle")
+            {
+                return Ok(shape.text()?.filter(|text| !text.is_empty()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Get the text content of this slide without the title.
+    ///
+    /// The title is available through [`Slide::title`].
+    pub fn body_text(&self) -> Result<String> {
+        self.part
+            .extract_body_text_with_diagrams(&|rel_id| self.smartart_text(rel_id))
+    }
+
+    /// Get the text content of this slide without the text of its tables.
+    ///
+    /// Table content is available through [`Slide::tables`], and SmartArt
+    /// text through [`Slide::smartart`].
+    pub fn text_without_tables(&self) -> Result<String> {
+        self.part.extract_text_without_tables()
     }
 
     /// Get access to the underlying slide part.
@@ -223,6 +294,34 @@ impl<'a> Slide<'a> {
         Ok(false)
     }
 
+    /// Get all tables on the slide, in shape order.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::pptx::Package;
+    ///
+    /// let pkg = Package::open("presentation.pptx")?;
+    /// let pres = pkg.presentation()?;
+    /// let slides = pres.slides()?;
+    ///
+    /// if let Some(slide) = slides.first() {
+    ///     for table in slide.tables()? {
+    ///         println!("Table: {}x{}", table.row_count()?, table.column_count()?);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn tables(&self) -> Result<Vec<Table>> {
+        let mut tables = Vec::new();
+        for shape in self.shapes()? {
+            if let Some(table) = shape.table()? {
+                tables.push(table);
+            }
+        }
+        Ok(tables)
+    }
+
     /// Check if the slide has any pictures.
     ///
     /// # Examples
@@ -442,18 +541,177 @@ impl<'a> Slide<'a> {
 
             if let Ok(notes_part) = package.get_part(&notes_partname) {
                 // Extract text from notes
-                return Self::extract_notes_text(notes_part.blob());
+                return Self::extract_notes_text(notes_part.try_blob()?);
             }
         }
 
         Ok(None)
     }
 
+    /// Get the charts placed on this slide.
+    ///
+    /// Series data is taken from the values cached in each chart part.
+    /// Returns an empty list if the package reference is not available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::pptx::Package;
+    ///
+    /// let pkg = Package::open("presentation.pptx")?;
+    /// let pres = pkg.presentation()?;
+    ///
+    /// for slide in pres.slides()? {
+    ///     for chart in slide.charts()? {
+    ///         println!("Chart with {} series", chart.plot_area.series().count());
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn charts(&self) -> Result<Vec<Chart>> {
+        match self.package {
+            Some(package) => read_related_charts(package, self.part.part()),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the SmartArt diagrams placed on this slide, in document order.
+    ///
+    /// Returns an empty list if the package reference is not available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::pptx::Package;
+    ///
+    /// let pkg = Package::open("presentation.pptx")?;
+    /// let pres = pkg.presentation()?;
+    ///
+    /// for slide in pres.slides()? {
+    ///     for smartart in slide.smartart()? {
+    ///         for node in smartart.nodes() {
+    ///             println!("{}", node.text);
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn smartart(&self) -> Result<Vec<SmartArt>> {
+        let Some(package) = self.package else {
+            return Ok(Vec::new());
+        };
+        let mut diagrams = Vec::new();
+        for rel_id in diagram_rel_ids(self.part.part().try_blob()?)? {
+            if let Some(smartart) = read_related_smartart(package, self.part.part(), &rel_id)? {
+                diagrams.push(smartart);
+            }
+        }
+        Ok(diagrams)
+    }
+
+    /// Get the part name and data of the image with relationship `r_id`,
+    /// such as the [`Picture::image_r_id`](crate::ooxml::pptx::shapes::Picture::image_r_id)
+    /// of a picture on this slide.
+    ///
+    /// Returns `None` for linked images, or if the package reference is not
+    /// available.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::pptx::Package;
+    /// use litchi::ooxml::pptx::shapes::{Picture, ShapeType};
+    ///
+    /// let pkg = Package::open("presentation.pptx")?;
+    /// let pres = pkg.presentation()?;
+    ///
+    /// for slide in pres.slides()? {
+    ///     for shape in slide.shapes()? {
+    ///         if *shape.shape_type() == ShapeType::Picture {
+    ///             let picture = Picture::new(shape.xml_bytes().to_vec());
+    ///             if let Some((partname, data)) = slide.image(&picture.image_r_id()?)? {
+    ///                 println!("{}: {} bytes", partname, data.len());
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn image(&self, r_id: &str) -> Result<Option<(PackURI, &'a [u8])>> {
+        let Some(package) = self.package else {
+            return Ok(None);
+        };
+        let Some(rel) = self
+            .part
+            .part()
+            .rels()
+            .get(r_id)
+            .filter(|rel| !rel.is_external())
+        else {
+            return Ok(None);
+        };
+        let part = package.get_part(&rel.target_partname()?)?;
+        Ok(Some((part.partname().clone(), part.try_blob()?)))
+    }
+
+    /// Get the bounding box of a shape on this slide.
+    ///
+    /// Like [`BaseShape::bounds`], but a placeholder without its own
+    /// transform takes the position of the placeholder it inherits from:
+    /// the one on the slide layout with the same `idx` or, failing that,
+    /// the same type, and then the one of the same type on the slide master.
+    pub fn shape_bounds(&self, shape: &mut BaseShape) -> Result<Option<Rect>> {
+        if let Some(bounds) = shape.bounds()? {
+            return Ok(Some(bounds));
+        }
+        let (Some(package), Some(key)) = (self.package, PlaceholderKey::parse(shape.xml_bytes()))
+        else {
+            return Ok(None);
+        };
+
+        let Some(layout) = related_part(package, self.part.part(), rt::SLIDE_LAYOUT)? else {
+            return Ok(None);
+        };
+        let layout_frames = placeholder_frames(layout)?;
+        let by_idx = key.idx.and_then(|idx| {
+            layout_frames
+                .iter()
+                .find(|(other, _)| other.idx == Some(idx))
+        });
+        if let Some((_, bounds)) = by_idx.or_else(|| {
+            layout_frames
+                .iter()
+                .find(|(other, _)| other.kind == key.kind)
+        }) {
+            return Ok(Some(*bounds));
+        }
+
+        let Some(master) = related_part(package, layout, rt::SLIDE_MASTER)? else {
+            return Ok(None);
+        };
+        Ok(placeholder_frames(master)?
+            .into_iter()
+            .find(|(other, _)| other.master_kind() == key.master_kind())
+            .map(|(_, bounds)| bounds))
+    }
+
+    /// Get the package this slide belongs to, if available.
+    #[cfg(feature = "render")]
+    pub(crate) fn package(&self) -> Option<&'a crate::ooxml::opc::OpcPackage> {
+        self.package
+    }
+
+    /// Get the text of the SmartArt diagram with data relationship `rel_id`.
+    fn smartart_text(&self, rel_id: &str) -> Result<Option<String>> {
+        match self.package {
+            Some(package) => Ok(read_related_smartart(package, self.part.part(), rel_id)?
+                .map(|smartart| smartart.text())),
+            None => Ok(None),
+        }
+    }
+
     /// Extract text from notes XML.
     fn extract_notes_text(xml: &[u8]) -> Result<Option<String>> {
-        use quick_xml::Reader;
-        use quick_xml::events::Event;
-
         let mut reader = Reader::from_reader(xml);
         reader.config_mut().trim_text(true);
 
@@ -494,6 +752,78 @@ impl<'a> Slide<'a> {
     }
 }
 
+/// The type and index of a placeholder (`p:ph`), used to find the
+/// placeholder it inherits from.
+struct PlaceholderKey {
+    kind: String,
+    idx: Option<u32>,
+}
+
+impl PlaceholderKey {
+    /// Read the `p:ph` element of a shape, if it is a placeholder.
+    fn parse(shape_xml: &[u8]) -> Option<Self> {
+        let mut reader = Reader::from_reader(shape_xml);
+        loop {
+            match reader.read_event() {
+                Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"ph" => {
+                    let mut key = Self {
+                        // Placeholders without a type are object placeholders
+                        kind: "obj".to_string(),
+                        idx: None,
+                    };
+                    for attr in e.attributes().flatten() {
+                        let value = String::from_utf8_lossy(&attr.value);
+                        match attr.key.as_ref() {
+                            b"type" => key.kind = value.into_owned(),
+                            b"idx" => key.idx = value.parse().ok(),
+                            _ => {},
+                        }
+                    }
+                    return Some(key);
+                },
+                Ok(Event::Eof) | Err(_) => return None,
+                _ => {},
+            }
+        }
+    }
+
+    /// The type of the master placeholder this one inherits from. Masters
+    /// only have title, body, date, footer and slide number placeholders.
+    fn master_kind(&self) -> &str {
+        match self.kind.as_str() {
+            "ctrTitle" | "title" => "title",
+            "dt" | "ftr" | "sldNum" => &self.kind,
+            _ => "body",
+        }
+    }
+}
+
+/// Get the part related to `part` by the relationship of type `reltype`.
+pub(crate) fn related_part<'p>(
+    package: &'p crate::ooxml::opc::OpcPackage,
+    part: &dyn Part,
+    reltype: &str,
+) -> Result<Option<&'p dyn Part>> {
+    let Some(rel) = part.rels().iter().find(|rel| rel.reltype() == reltype) else {
+        return Ok(None);
+    };
+    Ok(package.get_part(&rel.target_partname()?).ok())
+}
+
+/// Get the placeholders of a slide layout or master that have their own
+/// position.
+fn placeholder_frames(part: &dyn Part) -> Result<Vec<(PlaceholderKey, Rect)>> {
+    let mut frames = Vec::new();
+    for mut shape in SlidePart::from_part(part)?.shapes()? {
+        if let Some(key) = PlaceholderKey::parse(shape.xml_bytes())
+            && let Some(bounds) = shape.bounds()?
+        {
+            frames.push((key, bounds));
+        }
+    }
+    Ok(frames)
+}
+
 /// A slide layout.
 ///
 /// Slide layouts define the arrangement of placeholders and other elements
@@ -604,5 +934,180 @@ impl<'a> SlideMaster<'a> {
 
 #[cfg(test)]
 mod tests {
-    // Tests will be added as implementation progresses
+    use crate::common::Length;
+    use crate::ooxml::charts::{TitleText, TypeGroup};
+    use crate::ooxml::pptx::Package;
+    use std::path::PathBuf;
+
+    #[test]
+    fn slide_charts_read_cached_values() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/ooxml/pptx/scatter-chart.pptx");
+        let pkg = Package::open(&path).unwrap();
+        let pres = pkg.presentation().unwrap();
+        let slides = pres.slides().unwrap();
+
+        let charts = slides[0].charts().unwrap();
+        assert_eq!(charts.len(), 1);
+        assert!(matches!(
+            charts[0].plot_area.type_groups[0],
+            TypeGroup::Scatter(_)
+        ));
+
+        let series = charts[0].plot_area.series().next().unwrap();
+        match &series.title {
+            Some(TitleText::Literal(text)) => assert_eq!(text.text, "Sales"),
+            other => panic!("unexpected series title: {:?}", other),
+        }
+        let y_values = series.y_values.as_ref().unwrap();
+        assert_eq!(y_values.values, vec![8.200000000000001, 3.2, 1.4, 1.2]);
+        assert_eq!(
+            y_values.source_ref.as_ref().unwrap().formula,
+            "Sheet1!$B$2:$B$5"
+        );
+    }
+
+    #[test]
+    fn slide_smartart_nodes_and_text() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data/ooxml/pptx/smartart-org-chart2.pptx");
+        let pkg = Package::open(&path).unwrap();
+        let pres = pkg.presentation().unwrap();
+        let slides = pres.slides().unwrap();
+
+        let diagrams = slides[0].smartart().unwrap();
+        assert_eq!(diagrams.len(), 1);
+        let root = &diagrams[0].nodes()[0];
+        assert_eq!(root.text, "A");
+        let b1 = &root.children[0];
+        assert_eq!(b1.text, "B1");
+        assert_eq!(b1.children[1].text, "C2");
+        assert_eq!(b1.children[1].children[0].text, "D1");
+        assert_eq!(b1.children[1].children[0].depth, 3);
+
+        assert_eq!(
+            slides[0].text().unwrap(),
+            "A\nB1\nC1\nC2\nD1\nD2\nB2\nC3\nC4"
+        );
+        assert_eq!(slides[0].text_without_tables().unwrap(), "");
+    }
+
+    #[test]
+    fn slide_placeholder_bounds_from_layout() {
+        use crate::common::Rect;
+        use crate::ooxml::pptx::shapes::{Picture, ShapeType};
+
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/ooxml/pptx/sample.pptx");
+        let pkg = Package::open(&path).unwrap();
+        let pres = pkg.presentation().unwrap();
+        let slides = pres.slides().unwrap();
+
+        // The title and subtitle have no transform of their own
+        let mut shapes = slides[0].shapes().unwrap();
+        assert_eq!(shapes[0].bounds().unwrap(), None);
+        assert_eq!(
+            slides[0].shape_bounds(&mut shapes[0]).unwrap(),
+            Some(Rect::from_emus(685800, 2130425, 7772400, 1470025))
+        );
+        assert_eq!(
+            slides[0].shape_bounds(&mut shapes[1]).unwrap(),
+            Some(Rect::from_emus(1371600, 3886200, 6400800, 1752600))
+        );
+
+        let path =
+            PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/ooxml/pptx/shapes.pptx");
+        let pkg = Package::open(&path).unwrap();
+        let pres = pkg.presentation().unwrap();
+        let picture = pres
+            .slides()
+            .unwrap()
+            .iter()
+            .find_map(|slide| {
+                let shape = slide
+                    .shapes()
+                    .unwrap()
+                    .into_iter()
+                    .find(|shape| *shape.shape_type() == ShapeType::Picture)?;
+                let r_id = Picture::new(shape.xml_bytes().to_vec())
+                    .image_r_id()
+                    .unwrap();
+                slide.image(&r_id).unwrap()
+            })
+            .unwrap();
+        assert_eq!(picture.0.ext(), "jpg");
+        assert!(picture.1.starts_with(&[0xFF, 0xD8]));
+    }
+
+    #[test]
+    fn slide_tables_round_trip() {
+        let mut pkg = Package::new().unwrap();
+        let slide = pkg.presentation_mut().unwrap().add_slide().unwrap();
+        let table = slide.add_table(
+            3,
+            3,
+            Length::from_inches(1.0),
+            Length::from_inches(1.0),
+            Length::from_inches(6.0),
+            Length::from_inches(1.5),
+        );
+        table.set_cell_text(0, 0, "Region");
+        table.set_cell_text(0, 1, "Q1 2024");
+        table.set_cell_text(1, 2, "42");
+        table.set_cell_text(2, 0, "Total");
+        table.set_cell_fill(1, 2, "FFC000");
+        table.set_column_width(0, Length::from_inches(2.0));
+        table.merge_cells(2, 0, 2, 1).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("table.pptx");
+        pkg.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let pkg = Package::open(&path).unwrap();
+        let pres = pkg.presentation().unwrap();
+        let slides = pres.slides().unwrap();
+        let tables = slides[0].tables().unwrap();
+        assert_eq!(tables.len(), 1);
+
+        let table = &tables[0];
+        assert_eq!(table.row_count().unwrap(), 3);
+        assert_eq!(table.column_count().unwrap(), 3);
+        let text = |r, c| table.cell(r, c).unwrap().unwrap().text().unwrap();
+        assert_eq!(text(0, 0), "Region");
+        assert_eq!(text(0, 1), "Q1 2024");
+        assert_eq!(text(1, 2), "42");
+        assert_eq!(text(2, 0), "Total");
+
+        let merged = table.cell(2, 0).unwrap().unwrap();
+        assert_eq!(merged.grid_span().unwrap(), 2);
+        assert!(table.cell(2, 1).unwrap().unwrap().is_h_merge().unwrap());
+    }
+
+    #[test]
+    fn slide_title_and_body_text() {
+        use super::{Slide, SlidePart};
+        use crate::ooxml::opc::packuri::PackURI;
+        use crate::ooxml::opc::part::BlobPart;
+
+        // The title shape sits between two body shapes
+        let xml = br#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main"><p:cSld><p:spTree>
+            <p:sp><p:nvSpPr><p:cNvPr id="2" name="Body"/><p:cNvSpPr/><p:nvPr><p:ph type="body" idx="1"/></p:nvPr></p:nvSpPr><p:txBody><a:p><a:r><a:t>First point</a:t></a:r></a:p></p:txBody></p:sp>
+            <p:sp><p:nvSpPr><p:cNvPr id="3" name="Title"/><p:cNvSpPr/><p:nvPr><p:ph type="title"/></p:nvPr></p:nvSpPr><p:txBody><a:p><a:r><a:t>Quarterly Review</a:t></a:r></a:p></p:txBody></p:sp>
+            <p:sp><p:nvSpPr><p:cNvPr id="4" name="Note"/><p:cNvSpPr/><p:nvPr/></p:nvSpPr><p:txBody><a:p><a:r><a:t>Footnote</a:t></a:r></a:p></p:txBody></p:sp>
+        </p:spTree></p:cSld></p:sld>"#;
+        let part = BlobPart::new(
+            PackURI::new("/ppt/slides/slide1.xml").unwrap(),
+            "application/vnd.openxmlformats-officedocument.presentationml.slide+xml".to_string(),
+            xml.to_vec(),
+        );
+        let slide = Slide::new(SlidePart::from_part(&part).unwrap());
+
+        assert_eq!(slide.title().unwrap().as_deref(), Some("Quarterly Review"));
+        assert_eq!(
+            slide.text().unwrap(),
+            "First point\nQuarterly Review\nFootnote"
+        );
+        assert_eq!(slide.body_text().unwrap(), "First point\nFootnote");
+    }
 }
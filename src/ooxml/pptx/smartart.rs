@@ -1,12 +1,17 @@
 //! SmartArt/Diagram support for PowerPoint presentations.
 //!
 //! SmartArt graphics are represented as diagrams in OOXML. This module provides
-//! read support for extracting diagram information from presentations.
+//! read support for extracting diagram information from presentations and
+//! Word documents, and the parts written for new diagrams.
 
 use crate::common::xml::escape_xml;
+use crate::ooxml::docx::content_control::push_general_ref;
 use crate::ooxml::error::{OoxmlError, Result};
+use crate::ooxml::opc::OpcPackage;
+use crate::ooxml::opc::part::Part;
 use quick_xml::Reader;
-use quick_xml::events::Event;
+use quick_xml::events::{BytesStart, Event};
+use std::collections::{HashMap, HashSet};
 
 /// SmartArt diagram type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -84,13 +89,16 @@ impl DiagramNode {
     }
 
     /// Get all text from this node and its children.
+    ///
+    /// Node texts are separated by newlines; nodes without text are skipped.
     pub fn all_text(&self) -> String {
         let mut result = self.text.clone();
         for child in &self.children {
-            if !result.is_empty() && !child.text.is_empty() {
+            let child_text = child.all_text();
+            if !result.is_empty() && !child_text.is_empty() {
                 result.push('\n');
             }
-            result.push_str(&child.all_text());
+            result.push_str(&child_text);
         }
         result
     }
@@ -130,6 +138,7 @@ impl SmartArt {
         self.nodes
             .iter()
             .map(|n| n.all_text())
+            .filter(|text| !text.is_empty())
             .collect::<Vec<_>>()
             .join("\n")
     }
@@ -139,48 +148,304 @@ impl SmartArt {
         self.nodes.len()
     }
 
-    /// Parse SmartArt data XML (dgm:data).
+    /// Get the root nodes of the diagram.
+    ///
+    /// Each node holds its child nodes, following the parent/child
+    /// connections of the diagram data.
+    #[inline]
+    pub fn nodes(&self) -> &[DiagramNode] {
+        &self.nodes
+    }
+
+    /// Parse a SmartArt diagram from its data part (`dgm:dataModel`).
+    ///
+    /// The diagram type is taken from the layout (`loTypeId`) of the
+    /// document point.
+    pub fn from_data_xml(xml: &[u8]) -> Result<Self> {
+        let model = DataModel::parse(xml)?;
+        let diagram_type = model
+            .layout_type
+            .as_deref()
+            .map_or(DiagramType::Unknown, DiagramType::from_layout_uri);
+        Ok(Self {
+            diagram_type,
+            nodes: model.into_nodes(),
+            layout_name: None,
+            id: None,
+        })
+    }
+
+    /// Parse SmartArt data XML (dgm:dataModel) into its node tree.
+    ///
+    /// Data points (`dgm:pt` of type `node` or `asst`) become nodes, arranged
+    /// by the parent-of connections in `dgm:cxnLst` and ordered by `srcOrd`.
+    /// The children of the document point are the root nodes.
     pub fn parse_data_xml(xml: &str) -> Result<Vec<DiagramNode>> {
-        let mut reader = Reader::from_str(xml);
-        reader.config_mut().trim_text(true);
+        Ok(DataModel::parse(xml.as_bytes())?.into_nodes())
+    }
+}
 
-        let mut nodes = Vec::new();
-        let mut current_text = String::new();
-        let mut in_text = false;
+/// Points and parent-of connections of a diagram data part.
+#[derive(Default)]
+struct DataModel {
+    /// Model ID of the `doc` point
+    doc_id: Option<String>,
+    /// Layout type (`loTypeId`) of the `doc` point
+    layout_type: Option<String>,
+    /// Text of the data points, in document order
+    points: Vec<(String, String)>,
+    /// Parent-of connections as (source, destination, source order)
+    connections: Vec<(String, String, u32)>,
+}
+
+impl DataModel {
+    fn parse(xml: &[u8]) -> Result<Self> {
+        let mut reader = Reader::from_reader(xml);
+        // Text runs keep their spacing, including around entity references
+        reader.config_mut().trim_text(false);
+
+        let mut model = Self::default();
+        // Whether the current point is a data point and which is the doc point
+        let mut in_data_point = false;
+        let mut in_doc_point = false;
+        // Inside `dgm:t` (the point text body), and inside an `a:t` run
+        let mut in_body = false;
+        let mut in_run = false;
+        let mut paragraphs = 0usize;
 
         loop {
             match reader.read_event() {
-                Ok(Event::Start(e)) => {
-                    let local = e.local_name();
-                    if local.as_ref() == b"t" {
-                        in_text = true;
-                        current_text.clear();
-                    }
+                Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                    b"pt" => {
+                        let (id, kind) = Self::point_attributes(&e);
+                        in_doc_point = kind == "doc";
+                        in_data_point = matches!(kind.as_str(), "node" | "asst");
+                        if in_doc_point {
+                            model.doc_id = Some(id);
+                        } else if in_data_point {
+                            model.points.push((id, String::new()));
+                        }
+                    },
+                    b"t" if in_data_point && in_body => in_run = true,
+                    b"t" if in_data_point => {
+                        in_body = true;
+                        paragraphs = 0;
+                    },
+                    b"p" if in_body => {
+                        paragraphs += 1;
+                        if paragraphs > 1
+                            && let Some((_, text)) = model.points.last_mut()
+                        {
+                            text.push('\n');
+                        }
+                    },
+                    b"prSet" if in_doc_point => model.layout_type = Self::layout_type(&e),
+                    b"cxn" => model.add_connection(&e),
+                    _ => {},
                 },
-                Ok(Event::End(e)) => {
-                    let local = e.local_name();
-                    if local.as_ref() == b"t" {
-                        in_text = false;
-                        if !current_text.trim().is_empty() {
-                            nodes.push(DiagramNode::new(current_text.trim()));
+                Ok(Event::Empty(e)) => match e.local_name().as_ref() {
+                    b"pt" => {
+                        let (id, kind) = Self::point_attributes(&e);
+                        match kind.as_str() {
+                            "doc" => model.doc_id = Some(id),
+                            "node" | "asst" => model.points.push((id, String::new())),
+                            _ => {},
+                        }
+                    },
+                    b"br" if in_body => {
+                        if let Some((_, text)) = model.points.last_mut() {
+                            text.push('\n');
                         }
+                    },
+                    b"prSet" if in_doc_point => model.layout_type = Self::layout_type(&e),
+                    b"cxn" => model.add_connection(&e),
+                    _ => {},
+                },
+                Ok(Event::Text(e)) if in_run => {
+                    let text = e.decode().map_err(|e| OoxmlError::Xml(e.to_string()))?;
+                    if let Some((_, point_text)) = model.points.last_mut() {
+                        point_text.push_str(&text);
                     }
                 },
-                Ok(Event::Text(e)) => {
-                    if in_text && let Ok(text) = std::str::from_utf8(e.as_ref()) {
-                        current_text.push_str(text);
+                Ok(Event::GeneralRef(e)) if in_run => {
+                    if let Some((_, point_text)) = model.points.last_mut() {
+                        push_general_ref(point_text, &e)?;
                     }
                 },
+                Ok(Event::End(e)) => match e.local_name().as_ref() {
+                    b"pt" => {
+                        in_data_point = false;
+                        in_doc_point = false;
+                        in_body = false;
+                    },
+                    b"t" if in_run => in_run = false,
+                    b"t" => in_body = false,
+                    _ => {},
+                },
                 Ok(Event::Eof) => break,
                 Err(e) => return Err(OoxmlError::Xml(e.to_string())),
                 _ => {},
             }
         }
 
-        Ok(nodes)
+        Ok(model)
+    }
+
+    /// Read the `modelId` and `type` of a `dgm:pt`; the type defaults to `node`.
+    fn point_attributes(e: &BytesStart<'_>) -> (String, String) {
+        let mut id = String::new();
+        let mut kind = String::from("node");
+        for attr in e.attributes().flatten() {
+            match attr.key.local_name().as_ref() {
+                b"modelId" => id = String::from_utf8_lossy(&attr.value).into_owned(),
+                b"type" => kind = String::from_utf8_lossy(&attr.value).into_owned(),
+                _ => {},
+            }
+        }
+        (id, kind)
+    }
+
+    /// Read the `loTypeId` of a `dgm:prSet`.
+    fn layout_type(e: &BytesStart<'_>) -> Option<String> {
+        e.attributes()
+            .flatten()
+            .find(|attr| attr.key.local_name().as_ref() == b"loTypeId")
+            .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+    }
+
+    /// Record a `dgm:cxn` if it is a parent-of connection (the default type).
+    fn add_connection(&mut self, e: &BytesStart<'_>) {
+        let mut source = String::new();
+        let mut destination = String::new();
+        let mut order = 0;
+        for attr in e.attributes().flatten() {
+            match attr.key.local_name().as_ref() {
+                b"type" if attr.value.as_ref() != b"parOf" => return,
+                b"srcId" => source = String::from_utf8_lossy(&attr.value).into_owned(),
+                b"destId" => destination = String::from_utf8_lossy(&attr.value).into_owned(),
+                b"srcOrd" => {
+                    order = std::str::from_utf8(&attr.value)
+                        .ok()
+                        .and_then(|value| value.parse().ok())
+                        .unwrap_or(0);
+                },
+                _ => {},
+            }
+        }
+        self.connections.push((source, destination, order));
+    }
+
+    /// Build the node tree from the connections.
+    ///
+    /// Without a document point, data points that have no parent are the
+    /// roots. A point is placed once, so cyclic connections are ignored.
+    fn into_nodes(self) -> Vec<DiagramNode> {
+        let texts: HashMap<&str, &str> = self
+            .points
+            .iter()
+            .map(|(id, text)| (id.as_str(), text.as_str()))
+            .collect();
+
+        let mut children: HashMap<&str, Vec<(u32, &str)>> = HashMap::new();
+        for (source, destination, order) in &self.connections {
+            if texts.contains_key(destination.as_str()) {
+                children
+                    .entry(source.as_str())
+                    .or_default()
+                    .push((*order, destination.as_str()));
+            }
+        }
+        for siblings in children.values_mut() {
+            siblings.sort_by_key(|(order, _)| *order);
+        }
+
+        let roots: Vec<&str> = match &self.doc_id {
+            Some(doc_id) => children
+                .get(doc_id.as_str())
+                .map(|siblings| siblings.iter().map(|(_, id)| *id).collect())
+                .unwrap_or_default(),
+            None => {
+                let with_parent: HashSet<&str> =
+                    children.values().flatten().map(|(_, id)| *id).collect();
+                self.points
+                    .iter()
+                    .map(|(id, _)| id.as_str())
+                    .filter(|id| !with_parent.contains(id))
+                    .collect()
+            },
+        };
+
+        fn build(
+            id: &str,
+            depth: u32,
+            texts: &HashMap<&str, &str>,
+            children: &HashMap<&str, Vec<(u32, &str)>>,
+            placed: &mut HashSet<String>,
+        ) -> DiagramNode {
+            let mut node = DiagramNode::new(texts.get(id).copied().unwrap_or_default().trim());
+            node.depth = depth;
+            for (_, child) in children.get(id).into_iter().flatten() {
+                if placed.insert((*child).to_string()) {
+                    node.add_child(build(child, depth + 1, texts, children, placed));
+                }
+            }
+            node
+        }
+
+        let mut placed: HashSet<String> = roots.iter().map(|id| (*id).to_string()).collect();
+        roots
+            .iter()
+            .map(|id| build(id, 0, &texts, &children, &mut placed))
+            .collect()
     }
 }
 
+/// Read the SmartArt diagram whose data part is the target of `rel_id`.
+///
+/// `rel_id` is the `r:dm` attribute of a `dgm:relIds` element in `part`.
+/// Returns `None` if the relationship is missing or external.
+pub(crate) fn read_related_smartart(
+    package: &OpcPackage,
+    part: &dyn Part,
+    rel_id: &str,
+) -> Result<Option<SmartArt>> {
+    let Some(rel) = part.rels().get(rel_id).filter(|rel| !rel.is_external()) else {
+        return Ok(None);
+    };
+    let data_part = package.get_part(&rel.target_partname()?)?;
+    SmartArt::from_data_xml(data_part.try_blob()?).map(Some)
+}
+
+/// Get the data part relationship IDs (`r:dm`) of the diagrams in `xml`,
+/// in document order.
+pub(crate) fn diagram_rel_ids(xml: &[u8]) -> Result<Vec<String>> {
+    let mut reader = Reader::from_reader(xml);
+    let mut rel_ids = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) | Ok(Event::Empty(e)) if e.local_name().as_ref() == b"relIds" => {
+                if let Some(rel_id) = data_rel_id(&e) {
+                    rel_ids.push(rel_id);
+                }
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(OoxmlError::Xml(e.to_string())),
+            _ => {},
+        }
+    }
+
+    Ok(rel_ids)
+}
+
+/// Read the data part relationship ID (`r:dm`) of a `dgm:relIds` element.
+pub(crate) fn data_rel_id(e: &BytesStart<'_>) -> Option<String> {
+    e.attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == b"dm")
+        .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+}
+
 /// Builder for creating SmartArt diagrams.
 pub struct SmartArtBuilder {
     diagram_type: DiagramType,
@@ -1000,4 +1265,68 @@ mod tests {
         assert_eq!(smartart.node_count(), 3);
         assert!(smartart.text().contains("Item 1"));
     }
+
+    #[test]
+    fn test_parse_data_xml_builds_tree() {
+        // Connections out of order, a transition and a presentation point,
+        // and a node with two paragraphs
+        let xml = r#"<dgm:dataModel xmlns:dgm="d" xmlns:a="a"><dgm:ptLst>
+            <dgm:pt modelId="0" type="doc"><dgm:prSet loTypeId="urn:microsoft.com/office/officeart/2005/8/layout/hierarchy2"/><dgm:t><a:p/></dgm:t></dgm:pt>
+            <dgm:pt modelId="1"><dgm:t><a:p><a:r><a:t>Plan</a:t></a:r></a:p></dgm:t></dgm:pt>
+            <dgm:pt modelId="2"><dgm:t><a:p><a:r><a:t>Scope</a:t></a:r></a:p><a:p><a:r><a:t>Budget &amp; </a:t></a:r><a:r><a:t>time</a:t></a:r></a:p></dgm:t></dgm:pt>
+            <dgm:pt modelId="3" type="asst"><dgm:t><a:p><a:r><a:t>Review</a:t></a:r></a:p></dgm:t></dgm:pt>
+            <dgm:pt modelId="4"><dgm:t><a:p><a:r><a:t>Build</a:t></a:r></a:p></dgm:t></dgm:pt>
+            <dgm:pt modelId="5" type="sibTrans" cxnId="10"><dgm:t><a:p><a:r><a:t>ignored</a:t></a:r></a:p></dgm:t></dgm:pt>
+            <dgm:pt modelId="6" type="pres"><dgm:prSet presAssocID="1"/></dgm:pt>
+            </dgm:ptLst><dgm:cxnLst>
+            <dgm:cxn modelId="11" srcId="0" destId="4" srcOrd="1" destOrd="0"/>
+            <dgm:cxn modelId="10" srcId="0" destId="1" srcOrd="0" destOrd="0"/>
+            <dgm:cxn modelId="13" srcId="1" destId="3" srcOrd="1" destOrd="0"/>
+            <dgm:cxn modelId="12" srcId="1" destId="2" srcOrd="0" destOrd="0"/>
+            <dgm:cxn modelId="14" type="presOf" srcId="1" destId="6" srcOrd="0" destOrd="0"/>
+            </dgm:cxnLst></dgm:dataModel>"#;
+
+        let smartart = SmartArt::from_data_xml(xml.as_bytes()).unwrap();
+        assert_eq!(smartart.diagram_type, DiagramType::Hierarchy);
+
+        let nodes = smartart.nodes();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].text, "Plan");
+        assert_eq!(nodes[1].text, "Build");
+        let children: Vec<_> = nodes[0].children.iter().map(|n| n.text.as_str()).collect();
+        assert_eq!(children, ["Scope\nBudget & time", "Review"]);
+        assert_eq!(nodes[0].children[0].depth, 1);
+        assert_eq!(smartart.text(), "Plan\nScope\nBudget & time\nReview\nBuild");
+    }
+
+    #[test]
+    fn test_parse_generated_data_xml() {
+        let mut parent = DiagramNode::new("Parent");
+        parent.add_child(DiagramNode::new("Child"));
+        let mut smartart = SmartArt::new(DiagramType::Process);
+        smartart.add_node(parent);
+        smartart.add_node(DiagramNode::new("Sibling"));
+
+        let xml = generate_smartart_data_xml(&smartart);
+        let nodes = SmartArt::parse_data_xml(&xml).unwrap();
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[0].text, "Parent");
+        assert_eq!(nodes[0].children[0].text, "Child");
+        assert_eq!(nodes[1].text, "Sibling");
+    }
+
+    #[test]
+    fn test_all_text_skips_empty_nodes() {
+        let mut empty = DiagramNode::new("");
+        empty.add_child(DiagramNode::new("Inner"));
+        let mut root = DiagramNode::new("Outer");
+        root.add_child(empty);
+        assert_eq!(root.all_text(), "Outer\nInner");
+    }
+
+    #[test]
+    fn test_diagram_rel_ids() {
+        let xml = r#"<w:p><w:drawing><a:graphicData><dgm:relIds r:dm="rId4" r:lo="rId5"/></a:graphicData></w:drawing><w:drawing><dgm:relIds r:dm="rId9"/></w:drawing></w:p>"#;
+        assert_eq!(diagram_rel_ids(xml.as_bytes()).unwrap(), ["rId4", "rId9"]);
+    }
 }
@@ -5,8 +5,10 @@ pub mod pres;
 pub(crate) mod relmap;
 pub mod shape;
 pub mod slide;
+pub mod table;
 
 // Re-export main types
 pub use pres::{ChartParts, MutablePresentation, SmartArtParts};
 pub use shape::MutableShape;
 pub use slide::MutableSlide;
+pub use table::{MutableTable, MutableTableCell};
@@ -7,6 +7,7 @@ use std::fmt::Write as FmtWrite;
 
 // Import shared format types
 pub use super::super::format::{ImageFormat, TextFormat};
+use super::table::MutableTable;
 
 /// Optional relationship IDs for shapes that need external references.
 ///
@@ -73,18 +74,8 @@ pub(crate) enum ShapeType {
     Table {
         x: i64,
         y: i64,
-        width: i64,
-        height: i64,
-        /// Table data: rows of cells, each cell is a string
-        data: Vec<Vec<String>>,
-        /// Column widths in EMUs (optional, auto-calculated if not provided)
-        col_widths: Option<Vec<i64>>,
-        /// Row heights in EMUs (optional, auto-calculated if not provided)
-        row_heights: Option<Vec<i64>>,
-        /// First row is header
-        first_row: bool,
-        /// Band rows (alternating row colors)
-        band_row: bool,
+        /// Table content, column widths and row heights
+        table: MutableTable,
     },
     /// Group shape containing multiple child shapes
     GroupShape {
@@ -141,15 +132,13 @@ impl CollectGlyphs for MutableShape {
                     bitmap.insert(c as u32);
                 }
             },
-            ShapeType::Table { data, .. } => {
+            ShapeType::Table { table, .. } => {
                 // Table cells currently only support plain strings in PPTX writer
                 let font_name = "Calibri".to_string(); // Default font for tables
                 let bitmap = glyphs.entry(font_name).or_insert_with(RoaringBitmap::new);
-                for row in data {
-                    for cell_text in row {
-                        for c in cell_text.chars() {
-                            bitmap.insert(c as u32);
-                        }
+                for cell_text in table.texts() {
+                    for c in cell_text.chars() {
+                        bitmap.insert(c as u32);
                     }
                 }
             },
@@ -335,32 +324,10 @@ impl MutableShape {
     }
 
     /// Create a new table shape.
-    #[allow(clippy::too_many_arguments)]
-    pub(crate) fn new_table(
-        shape_id: u32,
-        x: i64,
-        y: i64,
-        width: i64,
-        height: i64,
-        data: Vec<Vec<String>>,
-        col_widths: Option<Vec<i64>>,
-        row_heights: Option<Vec<i64>>,
-        first_row: bool,
-        band_row: bool,
-    ) -> Self {
+    pub(crate) fn new_table(shape_id: u32, x: i64, y: i64, table: MutableTable) -> Self {
         Self {
             shape_id,
-            shape_type: ShapeType::Table {
-                x,
-                y,
-                width,
-                height,
-                data,
-                col_widths,
-                row_heights,
-                first_row,
-                band_row,
-            },
+            shape_type: ShapeType::Table { x, y, table },
         }
     }
 
@@ -619,29 +586,8 @@ impl MutableShape {
                 xml.push_str("</p:spPr>");
                 xml.push_str("</p:pic>");
             },
-            ShapeType::Table {
-                x,
-                y,
-                width,
-                height,
-                data,
-                col_widths,
-                row_heights,
-                first_row,
-                band_row,
-            } => {
-                self.write_table_xml(
-                    xml,
-                    *x,
-                    *y,
-                    *width,
-                    *height,
-                    data,
-                    col_widths.as_deref(),
-                    row_heights.as_deref(),
-                    *first_row,
-                    *band_row,
-                )?;
+            ShapeType::Table { x, y, table } => {
+                self.write_table_xml(xml, *x, *y, table)?;
             },
             ShapeType::GroupShape {
                 x,
@@ -757,37 +703,17 @@ impl MutableShape {
     }
 
     /// Write table XML (p:graphicFrame containing a:tbl).
-    #[allow(clippy::too_many_arguments)]
     fn write_table_xml(
         &self,
         xml: &mut String,
         x: i64,
         y: i64,
-        width: i64,
-        height: i64,
-        data: &[Vec<String>],
-        col_widths: Option<&[i64]>,
-        row_heights: Option<&[i64]>,
-        first_row: bool,
-        band_row: bool,
+        table: &MutableTable,
     ) -> Result<()> {
-        let num_rows = data.len();
-        let num_cols = data.first().map(|r| r.len()).unwrap_or(0);
-
-        if num_rows == 0 || num_cols == 0 {
+        if table.row_count() == 0 || table.column_count() == 0 {
             return Ok(());
         }
 
-        // Calculate column widths if not provided
-        let calculated_col_widths: Vec<i64> = col_widths
-            .map(|w| w.to_vec())
-            .unwrap_or_else(|| vec![width / num_cols as i64; num_cols]);
-
-        // Calculate row heights if not provided
-        let calculated_row_heights: Vec<i64> = row_heights
-            .map(|h| h.to_vec())
-            .unwrap_or_else(|| vec![height / num_rows as i64; num_rows]);
-
         // Start graphic frame
         xml.push_str("<p:graphicFrame>");
         xml.push_str("<p:nvGraphicFramePr>");
@@ -803,12 +729,17 @@ impl MutableShape {
         xml.push_str("<p:nvPr/>");
         xml.push_str("</p:nvGraphicFramePr>");
 
-        // Transform
+        // Transform (the frame extent is the size of the table grid)
         xml.push_str("<p:xfrm>");
         write!(xml, r#"<a:off x="{}" y="{}"/>"#, x, y)
             .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-        write!(xml, r#"<a:ext cx="{}" cy="{}"/>"#, width, height)
-            .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        write!(
+            xml,
+            r#"<a:ext cx="{}" cy="{}"/>"#,
+            table.width(),
+            table.height()
+        )
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
         xml.push_str("</p:xfrm>");
 
         // Graphic element containing table
@@ -817,64 +748,8 @@ impl MutableShape {
             r#"<a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/table">"#,
         );
 
-        // Table element
-        xml.push_str("<a:tbl>");
-
-        // Table properties
-        xml.push_str("<a:tblPr");
-        if first_row {
-            xml.push_str(" firstRow=\"1\"");
-        }
-        if band_row {
-            xml.push_str(" bandRow=\"1\"");
-        }
-        xml.push('>');
-        // Default table style
-        xml.push_str("<a:tableStyleId>{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}</a:tableStyleId>");
-        xml.push_str("</a:tblPr>");
-
-        // Table grid (column definitions)
-        xml.push_str("<a:tblGrid>");
-        for col_width in &calculated_col_widths {
-            write!(xml, r#"<a:gridCol w="{}"/>"#, col_width)
-                .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-        }
-        xml.push_str("</a:tblGrid>");
-
-        // Table rows
-        for (row_idx, row) in data.iter().enumerate() {
-            let row_height = calculated_row_heights
-                .get(row_idx)
-                .copied()
-                .unwrap_or(370840);
-            write!(xml, r#"<a:tr h="{}">"#, row_height)
-                .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-
-            for cell_text in row {
-                xml.push_str("<a:tc>");
-                xml.push_str("<a:txBody>");
-                xml.push_str("<a:bodyPr/>");
-                xml.push_str("<a:lstStyle/>");
-                xml.push_str("<a:p>");
-                if !cell_text.is_empty() {
-                    xml.push_str("<a:r>");
-                    xml.push_str(r#"<a:rPr lang="en-US" dirty="0"/>"#);
-                    write!(xml, "<a:t>{}</a:t>", escape_xml(cell_text))
-                        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
-                    xml.push_str("</a:r>");
-                } else {
-                    xml.push_str(r#"<a:endParaRPr lang="en-US"/>"#);
-                }
-                xml.push_str("</a:p>");
-                xml.push_str("</a:txBody>");
-                xml.push_str("<a:tcPr/>");
-                xml.push_str("</a:tc>");
-            }
-
-            xml.push_str("</a:tr>");
-        }
+        table.to_xml(xml)?;
 
-        xml.push_str("</a:tbl>");
         xml.push_str("</a:graphicData>");
         xml.push_str("</a:graphic>");
         xml.push_str("</p:graphicFrame>");
@@ -1094,27 +969,17 @@ mod tests {
             vec!["A1".to_string(), "B1".to_string()],
             vec!["A2".to_string(), "B2".to_string()],
         ];
-        let shape =
-            MutableShape::new_table(7, 1000, 2000, 3000, 2000, data, None, None, true, true);
+        let table = MutableTable::from_data(data, 3000, 2000, None, None);
+        let shape = MutableShape::new_table(7, 1000, 2000, table);
         assert_eq!(shape.shape_id, 7);
         match &shape.shape_type {
-            ShapeType::Table {
-                x,
-                y,
-                width,
-                height,
-                data,
-                first_row,
-                band_row,
-                ..
-            } => {
+            ShapeType::Table { x, y, table } => {
                 assert_eq!(*x, 1000);
                 assert_eq!(*y, 2000);
-                assert_eq!(*width, 3000);
-                assert_eq!(*height, 2000);
-                assert_eq!(data.len(), 2);
-                assert!(first_row);
-                assert!(band_row);
+                assert_eq!(table.width(), 3000);
+                assert_eq!(table.height(), 2000);
+                assert_eq!(table.row_count(), 2);
+                assert_eq!(table.column_count(), 2);
             },
             _ => panic!("Expected Table shape"),
         }
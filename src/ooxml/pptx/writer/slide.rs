@@ -10,7 +10,9 @@ use std::fmt::Write as FmtWrite;
 
 // Import shared format types
 use super::super::format::ImageFormat;
-use super::shape::MutableShape;
+use super::shape::{MutableShape, ShapeType};
+use super::table::MutableTable;
+use crate::common::Length;
 
 /// A mutable slide in a presentation.
 #[derive(Debug, Clone)]
@@ -304,7 +306,57 @@ impl MutableSlide {
         Ok(())
     }
 
-    /// Add a table to the slide.
+    /// Add an empty table to the slide.
+    ///
+    /// Columns and rows share the frame size evenly, and the first row is
+    /// styled as a header using the presentation's default table style.
+    ///
+    /// # Arguments
+    /// * `rows` - Number of rows
+    /// * `cols` - Number of columns
+    /// * `x` - X position
+    /// * `y` - Y position
+    /// * `width` - Table width
+    /// * `height` - Table height
+    ///
+    /// # Returns
+    /// A mutable reference to the table for filling in cells.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::common::Length;
+    /// use litchi::ooxml::pptx::MutablePresentation;
+    ///
+    /// let mut pres = MutablePresentation::new();
+    /// let slide = pres.add_slide().unwrap();
+    ///
+    /// // Create a 2x3 table
+    /// let table = slide.add_table(
+    ///     2,
+    ///     3,
+    ///     Length::from_inches(1.0),
+    ///     Length::from_inches(1.0),
+    ///     Length::from_inches(6.0),
+    ///     Length::from_inches(2.0),
+    /// );
+    /// table.set_cell_text(0, 0, "Header 1");
+    /// table.set_cell_text(1, 0, "Cell A");
+    /// ```
+    pub fn add_table(
+        &mut self,
+        rows: usize,
+        cols: usize,
+        x: Length,
+        y: Length,
+        width: Length,
+        height: Length,
+    ) -> &mut MutableTable {
+        let table = MutableTable::new(rows, cols, width.emus(), height.emus());
+        self.push_table(x.emus(), y.emus(), table)
+    }
+
+    /// Add a table filled with the given cell text to the slide.
     ///
     /// # Arguments
     /// * `data` - 2D vector of cell text content (rows x columns)
@@ -326,17 +378,18 @@ impl MutableSlide {
     ///     vec!["Header 1".to_string(), "Header 2".to_string(), "Header 3".to_string()],
     ///     vec!["Cell A".to_string(), "Cell B".to_string(), "Cell C".to_string()],
     /// ];
-    /// slide.add_table(data, 914400, 914400, 5486400, 1828800);
+    /// slide.add_table_from_data(data, 914400, 914400, 5486400, 1828800);
     /// ```
-    pub fn add_table(&mut self, data: Vec<Vec<String>>, x: i64, y: i64, width: i64, height: i64) {
-        let shape_id = (self.shapes.len() + 3) as u32;
-        let shape = MutableShape::new_table(
-            shape_id, x, y, width, height, data, None, None,
-            true, // first row is header by default
-            true, // band rows by default
-        );
-        self.shapes.push(shape);
-        self.modified = true;
+    pub fn add_table_from_data(
+        &mut self,
+        data: Vec<Vec<String>>,
+        x: i64,
+        y: i64,
+        width: i64,
+        height: i64,
+    ) -> &mut MutableTable {
+        let table = MutableTable::from_data(data, width, height, None, None);
+        self.push_table(x, y, table)
     }
 
     /// Add a table to the slide with custom options.
@@ -363,22 +416,23 @@ impl MutableSlide {
         row_heights: Option<Vec<i64>>,
         first_row_header: bool,
         band_rows: bool,
-    ) {
+    ) -> &mut MutableTable {
+        let mut table = MutableTable::from_data(data, width, height, col_widths, row_heights);
+        table.set_first_row_header(first_row_header);
+        table.set_band_rows(band_rows);
+        self.push_table(x, y, table)
+    }
+
+    /// Add a table shape and return the table it holds.
+    fn push_table(&mut self, x: i64, y: i64, table: MutableTable) -> &mut MutableTable {
         let shape_id = (self.shapes.len() + 3) as u32;
-        let shape = MutableShape::new_table(
-            shape_id,
-            x,
-            y,
-            width,
-            height,
-            data,
-            col_widths,
-            row_heights,
-            first_row_header,
-            band_rows,
-        );
-        self.shapes.push(shape);
+        self.shapes
+            .push(MutableShape::new_table(shape_id, x, y, table));
         self.modified = true;
+        match &mut self.shapes.last_mut().unwrap().shape_type {
+            ShapeType::Table { table, .. } => table,
+            _ => unreachable!("a table shape was just added"),
+        }
     }
 
     /// Add a group shape to the slide.
@@ -1297,8 +1351,21 @@ mod tests {
             vec!["A1".to_string(), "B1".to_string()],
             vec!["A2".to_string(), "B2".to_string()],
         ];
-        slide.add_table(data, 1000, 2000, 4000, 2000);
+        slide.add_table_from_data(data, 1000, 2000, 4000, 2000);
         assert_eq!(slide.shape_count(), 1);
+
+        let table = slide.add_table(
+            3,
+            2,
+            Length::from_emus(1000),
+            Length::from_emus(2000),
+            Length::from_emus(4000),
+            Length::from_emus(3000),
+        );
+        table.set_cell_text(2, 1, "C2");
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.column_count(), 2);
+        assert_eq!(slide.shape_count(), 2);
     }
 
     #[test]
@@ -0,0 +1,419 @@
+//! Table types and implementation for PPTX presentations.
+use crate::common::Length;
+use crate::common::xml::escape_xml;
+use crate::ooxml::error::{OoxmlError, Result};
+use std::fmt::Write as FmtWrite;
+
+/// ID of the "Medium Style 2 - Accent 1" table style, the default style
+/// defined in the presentation's `tableStyles.xml` part.
+const DEFAULT_TABLE_STYLE_ID: &str = "{5C22544A-7EE6-4342-B048-85BDC9FD1C3A}";
+
+/// Row height used when a table has no height to distribute (0.4").
+const DEFAULT_ROW_HEIGHT: i64 = 370840;
+
+/// A mutable table on a slide (DrawingML `a:tbl`).
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::common::Length;
+/// use litchi::ooxml::pptx::MutablePresentation;
+///
+/// let mut pres = MutablePresentation::new();
+/// let slide = pres.add_slide().unwrap();
+///
+/// let table = slide.add_table(
+///     3,
+///     3,
+///     Length::from_inches(1.0),
+///     Length::from_inches(1.0),
+///     Length::from_inches(6.0),
+///     Length::from_inches(1.5),
+/// );
+/// table.set_cell_text(0, 0, "Region");
+/// table.set_cell_text(0, 1, "Sales");
+/// table.set_cell_fill(1, 0, "FFC000");
+/// table.set_column_width(0, Length::from_inches(2.0));
+/// table.merge_cells(2, 0, 2, 2).unwrap();
+/// table.set_first_row_header(true);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MutableTable {
+    /// Cells, row by row
+    rows: Vec<Vec<MutableTableCell>>,
+    /// Column widths in EMUs
+    col_widths: Vec<i64>,
+    /// Row heights in EMUs
+    row_heights: Vec<i64>,
+    /// First row is styled as a header
+    first_row: bool,
+    /// Band rows (alternating row colors)
+    band_row: bool,
+}
+
+impl MutableTable {
+    /// Create an empty table, dividing the frame size evenly among columns
+    /// and rows.
+    pub(crate) fn new(rows: usize, cols: usize, width: i64, height: i64) -> Self {
+        Self {
+            rows: vec![vec![MutableTableCell::new(); cols]; rows],
+            col_widths: distribute(width, cols),
+            row_heights: distribute(height, rows),
+            first_row: true,
+            band_row: true,
+        }
+    }
+
+    /// Create a table filled with the given cell text.
+    ///
+    /// Missing column widths and row heights are derived from the frame size.
+    pub(crate) fn from_data(
+        data: Vec<Vec<String>>,
+        width: i64,
+        height: i64,
+        col_widths: Option<Vec<i64>>,
+        row_heights: Option<Vec<i64>>,
+    ) -> Self {
+        let cols = data.first().map(|r| r.len()).unwrap_or(0);
+        let mut table = Self::new(data.len(), cols, width, height);
+        for (row, texts) in table.rows.iter_mut().zip(data) {
+            for (cell, text) in row.iter_mut().zip(texts) {
+                cell.text = text;
+            }
+        }
+        if let Some(widths) = col_widths {
+            table.col_widths = widths;
+        }
+        if let Some(heights) = row_heights {
+            table.row_heights = heights;
+        }
+        table
+    }
+
+    /// Get the number of rows.
+    #[inline]
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Get the number of columns.
+    #[inline]
+    pub fn column_count(&self) -> usize {
+        self.col_widths.len()
+    }
+
+    /// Get a cell by row and column index.
+    pub fn cell(&mut self, row: usize, col: usize) -> Option<&mut MutableTableCell> {
+        self.rows.get_mut(row).and_then(|r| r.get_mut(col))
+    }
+
+    /// Set the text of a cell. Out-of-range indices are ignored.
+    pub fn set_cell_text(&mut self, row: usize, col: usize, text: &str) {
+        if let Some(cell) = self.cell(row, col) {
+            cell.set_text(text);
+        }
+    }
+
+    /// Set the background color of a cell (hex RGB, e.g. "FF0000").
+    /// Out-of-range indices are ignored.
+    pub fn set_cell_fill(&mut self, row: usize, col: usize, color: &str) {
+        if let Some(cell) = self.cell(row, col) {
+            cell.set_fill_color(color);
+        }
+    }
+
+    /// Set the width of a column. Out-of-range indices are ignored.
+    pub fn set_column_width(&mut self, col: usize, width: Length) {
+        if let Some(w) = self.col_widths.get_mut(col) {
+            *w = width.emus();
+        }
+    }
+
+    /// Set the height of a row. Out-of-range indices are ignored.
+    pub fn set_row_height(&mut self, row: usize, height: Length) {
+        if let Some(h) = self.row_heights.get_mut(row) {
+            *h = height.emus();
+        }
+    }
+
+    /// Set whether the first row is styled as a header.
+    pub fn set_first_row_header(&mut self, first_row: bool) {
+        self.first_row = first_row;
+    }
+
+    /// Set whether rows are banded with alternating colors.
+    pub fn set_band_rows(&mut self, band_row: bool) {
+        self.band_row = band_row;
+    }
+
+    /// Merge the rectangular range of cells from (`first_row`, `first_col`)
+    /// to (`last_row`, `last_col`), inclusive.
+    ///
+    /// The top-left cell spans the range and keeps its text; the other cells
+    /// are marked as merged into it.
+    pub fn merge_cells(
+        &mut self,
+        first_row: usize,
+        first_col: usize,
+        last_row: usize,
+        last_col: usize,
+    ) -> Result<()> {
+        if first_row > last_row
+            || first_col > last_col
+            || last_row >= self.row_count()
+            || last_col >= self.column_count()
+        {
+            return Err(OoxmlError::InvalidFormat(format!(
+                "Invalid merge range ({}, {})..({}, {}) for a {}x{} table",
+                first_row,
+                first_col,
+                last_row,
+                last_col,
+                self.row_count(),
+                self.column_count()
+            )));
+        }
+        let range =
+            || (first_row..=last_row).flat_map(|r| (first_col..=last_col).map(move |c| (r, c)));
+        if range().any(|(r, c)| self.rows[r][c].is_merged()) {
+            return Err(OoxmlError::InvalidFormat(
+                "Merge range overlaps merged cells".to_string(),
+            ));
+        }
+
+        for (r, c) in range() {
+            let cell = &mut self.rows[r][c];
+            cell.h_merge = c > first_col;
+            cell.v_merge = r > first_row;
+        }
+        let anchor = &mut self.rows[first_row][first_col];
+        anchor.grid_span = (last_col - first_col + 1) as u32;
+        anchor.row_span = (last_row - first_row + 1) as u32;
+        Ok(())
+    }
+
+    /// Total width of the table in EMUs.
+    pub(crate) fn width(&self) -> i64 {
+        self.col_widths.iter().sum()
+    }
+
+    /// Total height of the table in EMUs.
+    pub(crate) fn height(&self) -> i64 {
+        self.row_heights.iter().sum()
+    }
+
+    /// Iterate over the text of all cells.
+    #[cfg(feature = "fonts")]
+    pub(crate) fn texts(&self) -> impl Iterator<Item = &str> {
+        self.rows.iter().flatten().map(|cell| cell.text.as_str())
+    }
+
+    /// Write the table XML (`a:tbl`).
+    pub(crate) fn to_xml(&self, xml: &mut String) -> Result<()> {
+        xml.push_str("<a:tbl>");
+
+        // Table properties
+        xml.push_str("<a:tblPr");
+        if self.first_row {
+            xml.push_str(" firstRow=\"1\"");
+        }
+        if self.band_row {
+            xml.push_str(" bandRow=\"1\"");
+        }
+        xml.push('>');
+        write!(
+            xml,
+            "<a:tableStyleId>{}</a:tableStyleId>",
+            DEFAULT_TABLE_STYLE_ID
+        )
+        .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        xml.push_str("</a:tblPr>");
+
+        // Table grid (column definitions)
+        xml.push_str("<a:tblGrid>");
+        for col_width in &self.col_widths {
+            write!(xml, r#"<a:gridCol w="{}"/>"#, col_width)
+                .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        }
+        xml.push_str("</a:tblGrid>");
+
+        // Table rows
+        for (row, row_height) in self.rows.iter().zip(&self.row_heights) {
+            write!(xml, r#"<a:tr h="{}">"#, row_height)
+                .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+            for cell in row {
+                cell.to_xml(xml)?;
+            }
+            xml.push_str("</a:tr>");
+        }
+
+        xml.push_str("</a:tbl>");
+        Ok(())
+    }
+}
+
+/// A cell in a mutable table (`a:tc`).
+#[derive(Debug, Clone)]
+pub struct MutableTableCell {
+    /// Cell text
+    text: String,
+    /// Background color (hex RGB)
+    fill_color: Option<String>,
+    /// Number of columns spanned by a merged cell
+    grid_span: u32,
+    /// Number of rows spanned by a merged cell
+    row_span: u32,
+    /// Cell is merged into the cell to its left
+    h_merge: bool,
+    /// Cell is merged into the cell above
+    v_merge: bool,
+}
+
+impl MutableTableCell {
+    fn new() -> Self {
+        Self {
+            text: String::new(),
+            fill_color: None,
+            grid_span: 1,
+            row_span: 1,
+            h_merge: false,
+            v_merge: false,
+        }
+    }
+
+    /// Set the cell text.
+    pub fn set_text(&mut self, text: &str) {
+        self.text = text.to_string();
+    }
+
+    /// Get the cell text.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Set the background color (hex RGB, e.g. "FF0000").
+    pub fn set_fill_color(&mut self, color: &str) {
+        self.fill_color = Some(color.to_string());
+    }
+
+    /// Get the background color.
+    #[inline]
+    pub fn fill_color(&self) -> Option<&str> {
+        self.fill_color.as_deref()
+    }
+
+    /// Whether the cell takes part in a merged range.
+    fn is_merged(&self) -> bool {
+        self.grid_span > 1 || self.row_span > 1 || self.h_merge || self.v_merge
+    }
+
+    fn to_xml(&self, xml: &mut String) -> Result<()> {
+        xml.push_str("<a:tc");
+        if self.grid_span > 1 {
+            write!(xml, r#" gridSpan="{}""#, self.grid_span)
+                .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        }
+        if self.row_span > 1 {
+            write!(xml, r#" rowSpan="{}""#, self.row_span)
+                .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+        }
+        if self.h_merge {
+            xml.push_str(r#" hMerge="1""#);
+        }
+        if self.v_merge {
+            xml.push_str(r#" vMerge="1""#);
+        }
+        xml.push('>');
+
+        xml.push_str("<a:txBody>");
+        xml.push_str("<a:bodyPr/>");
+        xml.push_str("<a:lstStyle/>");
+        xml.push_str("<a:p>");
+        if !self.text.is_empty() {
+            xml.push_str("<a:r>");
+            xml.push_str(r#"<a:rPr lang="en-US" dirty="0"/>"#);
+            write!(xml, "<a:t>{}</a:t>", escape_xml(&self.text))
+                .map_err(|e| OoxmlError::Xml(e.to_string()))?;
+            xml.push_str("</a:r>");
+        } else {
+            xml.push_str(r#"<a:endParaRPr lang="en-US"/>"#);
+        }
+        xml.push_str("</a:p>");
+        xml.push_str("</a:txBody>");
+
+        match &self.fill_color {
+            Some(color) => write!(
+                xml,
+                r#"<a:tcPr><a:solidFill><a:srgbClr val="{}"/></a:solidFill></a:tcPr>"#,
+                escape_xml(color)
+            )
+            .map_err(|e| OoxmlError::Xml(e.to_string()))?,
+            None => xml.push_str("<a:tcPr/>"),
+        }
+        xml.push_str("</a:tc>");
+        Ok(())
+    }
+}
+
+/// Divide a length into `count` parts, giving the remainder to the last one.
+fn distribute(total: i64, count: usize) -> Vec<i64> {
+    if count == 0 {
+        return Vec::new();
+    }
+    let part = if total > 0 {
+        total / count as i64
+    } else {
+        DEFAULT_ROW_HEIGHT
+    };
+    let mut parts = vec![part; count];
+    if total > 0 {
+        parts[count - 1] += total - part * count as i64;
+    }
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_table_geometry() {
+        let table = MutableTable::new(3, 3, 1000, 900);
+        assert_eq!(table.row_count(), 3);
+        assert_eq!(table.column_count(), 3);
+        assert_eq!(table.col_widths, vec![333, 333, 334]);
+        assert_eq!(table.width(), 1000);
+        assert_eq!(table.height(), 900);
+    }
+
+    #[test]
+    fn test_table_xml() {
+        let mut table = MutableTable::new(2, 3, 3000, 2000);
+        table.set_cell_text(0, 0, "A & B");
+        table.set_cell_fill(1, 2, "FF0000");
+        table.set_column_width(1, Length::from_emus(1500));
+        table.set_first_row_header(false);
+        table.merge_cells(0, 0, 1, 1).unwrap();
+
+        let mut xml = String::new();
+        table.to_xml(&mut xml).unwrap();
+        assert!(xml.contains(r#"<a:tblPr bandRow="1">"#));
+        assert!(xml.contains(r#"<a:gridCol w="1500"/>"#));
+        assert!(xml.contains(r#"<a:tc gridSpan="2" rowSpan="2"><a:txBody>"#));
+        assert!(xml.contains("<a:t>A &amp; B</a:t>"));
+        assert!(xml.contains(r#"<a:tc hMerge="1">"#));
+        assert!(xml.contains(r#"<a:tc vMerge="1">"#));
+        assert!(xml.contains(r#"<a:tc hMerge="1" vMerge="1">"#));
+        assert!(xml.contains(r#"<a:srgbClr val="FF0000"/>"#));
+    }
+
+    #[test]
+    fn test_invalid_merge() {
+        let mut table = MutableTable::new(2, 2, 2000, 2000);
+        assert!(table.merge_cells(0, 0, 2, 0).is_err());
+        assert!(table.merge_cells(1, 0, 0, 0).is_err());
+        table.merge_cells(0, 0, 0, 1).unwrap();
+        assert!(table.merge_cells(0, 1, 1, 1).is_err());
+    }
+}
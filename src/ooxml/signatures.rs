@@ -0,0 +1,573 @@
+//! Digital signatures of OOXML packages.
+//!
+//! Office signs a package with XML-DSig signatures stored under
+//! `/_xmlsignatures`, which are found through the `digital-signature/origin`
+//! relationship of the package. Each signature holds a manifest with a
+//! digest of every signed part, the signer certificate and the signing
+//! time.
+//!
+//! [`read_signatures`] reports who signed a package and which parts each
+//! signature covers, and recomputes the part digests to tell whether the
+//! content still matches what was signed. The signature value itself is not
+//! verified, and certificate chains are only checked through a
+//! [`CertificateValidator`] supplied by the caller.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use litchi::ooxml::opc::OpcPackage;
+//! use litchi::ooxml::signatures;
+//!
+//! let package = OpcPackage::open("contract.xlsx")?;
+//! for signature in signatures::read_signatures(&package) {
+//!     println!(
+//!         "{} signed {} parts, digests valid: {}",
+//!         signature.subject.as_deref().unwrap_or("unknown signer"),
+//!         signature.covered_parts.len(),
+//!         signature.digests_valid
+//!     );
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use crate::ooxml::opc::OpcPackage;
+use crate::ooxml::opc::constants::namespace;
+use crate::ooxml::opc::packuri::{PACKAGE_URI, PackURI};
+use crate::ooxml::opc::rel::Relationships;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use chrono::{DateTime, Utc};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use std::fmt::Write;
+
+/// Transform that signs a selection of relationships instead of the whole
+/// relationships part.
+const RELATIONSHIP_TRANSFORM: &str =
+    "http://schemas.openxmlformats.org/package/2006/RelationshipTransform";
+
+/// A digital signature of a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureInfo {
+    /// Partname of the XML signature part
+    pub partname: String,
+    /// Subject of the signer certificate, such as `CN=Jane Doe, O=Example`
+    pub subject: Option<String>,
+    /// Issuer of the signer certificate
+    pub issuer: Option<String>,
+    /// DER-encoded certificates embedded in the signature, signer first
+    pub certificates: Vec<Vec<u8>>,
+    /// When the package was signed, as claimed by the signer
+    pub signing_time: Option<DateTime<Utc>>,
+    /// Partnames of the signed parts, including relationships parts
+    pub covered_parts: Vec<String>,
+    /// Signed parts that are missing or no longer match their digest
+    pub invalid_parts: Vec<String>,
+    /// Whether the signature covers at least one part and every signed part
+    /// still matches its digest
+    pub digests_valid: bool,
+}
+
+impl SignatureInfo {
+    /// Whether the part digests are valid and `validator` trusts the signer
+    /// certificates.
+    pub fn is_trusted(&self, validator: &dyn CertificateValidator) -> bool {
+        self.digests_valid && validator.validate(&self.certificates)
+    }
+}
+
+/// Validation of signer certificates, such as building a chain to a trusted
+/// root.
+pub trait CertificateValidator {
+    /// Whether to trust the DER-encoded `certificates` of a signature, which
+    /// start with the signer certificate.
+    fn validate(&self, certificates: &[Vec<u8>]) -> bool;
+}
+
+/// Read the digital signatures of a package and check their part digests.
+///
+/// Returns an empty list for unsigned packages.
+pub fn read_signatures(package: &OpcPackage) -> Vec<SignatureInfo> {
+    package
+        .signature_parts()
+        .into_iter()
+        .map(|part| {
+            let signature = ParsedSignature::parse(part.blob());
+            signature.check(package, part.partname().as_str())
+        })
+        .collect()
+}
+
+/// A part reference of the signature manifest.
+#[derive(Debug, Default)]
+struct Reference {
+    uri: String,
+    transforms: Vec<String>,
+    /// Ids of the relationships selected by a relationship transform
+    source_ids: Vec<String>,
+    /// Types of the relationships selected by a relationship transform
+    source_types: Vec<String>,
+    digest_method: String,
+    digest_value: String,
+}
+
+impl Reference {
+    /// The partname of the referenced part, without the content type query.
+    fn partname(&self) -> &str {
+        self.uri.split_once('?').map_or(&self.uri, |(path, _)| path)
+    }
+
+    /// Whether the referenced part still matches the digest.
+    fn matches(&self, package: &OpcPackage) -> bool {
+        let Ok(expected) = BASE64.decode(self.digest_value.as_bytes()) else {
+            return false;
+        };
+        let partname = self.partname();
+        let actual = match rels_source(partname) {
+            // Relationships are parsed when the package is loaded, so they
+            // can only be checked through the relationship transform
+            Some(source) => {
+                if !self.transforms.iter().any(|t| t == RELATIONSHIP_TRANSFORM) {
+                    return false;
+                }
+                let rels = if source == PACKAGE_URI {
+                    Some(package.rels())
+                } else {
+                    PackURI::new(source)
+                        .ok()
+                        .and_then(|uri| package.get_part(&uri).ok())
+                        .map(|part| part.rels())
+                };
+                rels.and_then(|rels| digest(&self.digest_method, self.transform(rels).as_bytes()))
+            },
+            None => PackURI::new(partname)
+                .ok()
+                .and_then(|uri| package.get_part(&uri).ok())
+                .and_then(|part| digest(&self.digest_method, part.try_blob().ok()?)),
+        };
+        actual.is_some_and(|actual| actual == expected)
+    }
+
+    /// Apply the relationship transform followed by XML canonicalization.
+    ///
+    /// The selected relationships are sorted by id, their attributes are
+    /// written in canonical order and `TargetMode` is always spelled out.
+    fn transform(&self, rels: &Relationships) -> String {
+        let mut selected: Vec<_> = rels
+            .iter()
+            .filter(|rel| {
+                self.source_ids.iter().any(|id| id == rel.r_id())
+                    || self.source_types.iter().any(|t| t == rel.reltype())
+            })
+            .collect();
+        selected.sort_by(|a, b| a.r_id().cmp(b.r_id()));
+
+        let mut xml = format!(
+            r#"<Relationships xmlns="{}">"#,
+            namespace::OPC_RELATIONSHIPS
+        );
+        for rel in selected {
+            let _ = write!(
+                xml,
+                r#"<Relationship Id="{}" Target="{}" TargetMode="{}" Type="{}"></Relationship>"#,
+                escape_attribute(rel.r_id()),
+                escape_attribute(rel.target_ref()),
+                if rel.is_external() {
+                    "External"
+                } else {
+                    "Internal"
+                },
+                escape_attribute(rel.reltype())
+            );
+        }
+        xml.push_str("</Relationships>");
+        xml
+    }
+}
+
+/// Which element the text being collected belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TextTarget {
+    DigestValue,
+    Certificate,
+    SigningTime,
+}
+
+/// The parts of an XML signature that are reported or checked.
+#[derive(Debug, Default)]
+struct ParsedSignature {
+    references: Vec<Reference>,
+    certificates: Vec<Vec<u8>>,
+    signing_time: Option<String>,
+}
+
+impl ParsedSignature {
+    /// Parse an XML signature part.
+    ///
+    /// Only references to package parts are collected; the references of
+    /// `SignedInfo` point at elements of the signature itself.
+    fn parse(xml: &[u8]) -> Self {
+        let mut signature = Self::default();
+        let mut reader = Reader::from_reader(xml);
+        let mut buf = Vec::new();
+        let mut reference: Option<Reference> = None;
+        let mut in_signature_time = false;
+        let mut target = None;
+        let mut text = String::new();
+
+        loop {
+            match reader.read_event_into(&mut buf) {
+                Ok(Event::Start(e)) => {
+                    target = match e.local_name().as_ref() {
+                        b"DigestValue" => Some(TextTarget::DigestValue),
+                        b"X509Certificate" => Some(TextTarget::Certificate),
+                        // Office writes both the OPC and the XAdES signing time
+                        b"SigningTime" => Some(TextTarget::SigningTime),
+                        b"Value" if in_signature_time => Some(TextTarget::SigningTime),
+                        b"SignatureTime" => {
+                            in_signature_time = true;
+                            None
+                        },
+                        _ => None,
+                    };
+                    text.clear();
+                    signature.open(&e, &mut reference);
+                },
+                Ok(Event::Empty(e)) => signature.open(&e, &mut reference),
+                Ok(Event::Text(t)) if target.is_some() => {
+                    if let Ok(t) = t.decode() {
+                        text.push_str(&t);
+                    }
+                },
+                Ok(Event::End(e)) => match (target.take(), e.local_name().as_ref()) {
+                    (Some(TextTarget::DigestValue), _) => {
+                        if let Some(reference) = reference.as_mut() {
+                            reference.digest_value = text.split_whitespace().collect();
+                        }
+                    },
+                    (Some(TextTarget::Certificate), _) => {
+                        let der: String = text.split_whitespace().collect();
+                        if let Ok(der) = BASE64.decode(der) {
+                            signature.certificates.push(der);
+                        }
+                    },
+                    (Some(TextTarget::SigningTime), _) => {
+                        signature
+                            .signing_time
+                            .get_or_insert_with(|| text.trim().to_string());
+                    },
+                    (None, b"SignatureTime") => in_signature_time = false,
+                    (None, b"Reference") => signature.references.extend(reference.take()),
+                    _ => {},
+                },
+                Ok(Event::Eof) | Err(_) => break,
+                _ => {},
+            }
+            buf.clear();
+        }
+        signature
+    }
+
+    /// Handle an element that may carry reference details in its attributes.
+    fn open(&mut self, e: &BytesStart, reference: &mut Option<Reference>) {
+        let attribute = |name: &[u8]| {
+            e.attributes()
+                .flatten()
+                .find(|attr| attr.key.local_name().as_ref() == name)
+                .map(|attr| String::from_utf8_lossy(&attr.value).into_owned())
+        };
+        match e.local_name().as_ref() {
+            b"Reference" => {
+                *reference = attribute(b"URI")
+                    .filter(|uri| uri.starts_with('/'))
+                    .map(|uri| Reference {
+                        uri,
+                        ..Reference::default()
+                    });
+            },
+            name => {
+                let Some(reference) = reference.as_mut() else {
+                    return;
+                };
+                match name {
+                    b"Transform" => reference.transforms.extend(attribute(b"Algorithm")),
+                    b"DigestMethod" => {
+                        reference.digest_method = attribute(b"Algorithm").unwrap_or_default();
+                    },
+                    b"RelationshipReference" => reference.source_ids.extend(attribute(b"SourceId")),
+                    b"RelationshipsGroupReference" => {
+                        reference.source_types.extend(attribute(b"SourceType"));
+                    },
+                    _ => {},
+                }
+            },
+        }
+    }
+
+    /// Check the references against the package.
+    fn check(self, package: &OpcPackage, partname: &str) -> SignatureInfo {
+        let invalid_parts: Vec<String> = self
+            .references
+            .iter()
+            .filter(|reference| !reference.matches(package))
+            .map(|reference| reference.partname().to_string())
+            .collect();
+        let (subject, issuer) = self
+            .certificates
+            .first()
+            .and_then(|der| x509_parser::parse_x509_certificate(der).ok())
+            .map(|(_, certificate)| {
+                (
+                    Some(certificate.subject().to_string()),
+                    Some(certificate.issuer().to_string()),
+                )
+            })
+            .unwrap_or_default();
+
+        SignatureInfo {
+            partname: partname.to_string(),
+            subject,
+            issuer,
+            signing_time: self
+                .signing_time
+                .and_then(|time| crate::ooxml::metadata::parse_datetime(&time).ok()),
+            covered_parts: self
+                .references
+                .iter()
+                .map(|reference| reference.partname().to_string())
+                .collect(),
+            digests_valid: !self.references.is_empty() && invalid_parts.is_empty(),
+            invalid_parts,
+            certificates: self.certificates,
+        }
+    }
+}
+
+/// Get the source partname of a relationships part, `/` for the package
+/// relationships, or `None` if `partname` is not a relationships part.
+fn rels_source(partname: &str) -> Option<String> {
+    let (dir, name) = partname.rsplit_once("/_rels/")?;
+    let name = name.strip_suffix(".rels")?;
+    Some(if name.is_empty() {
+        PACKAGE_URI.to_string()
+    } else {
+        format!("{dir}/{name}")
+    })
+}
+
+/// Escape an attribute value the way XML canonicalization does, which
+/// leaves `>` and `'` as they are.
+fn escape_attribute(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' => escaped.push_str("&#x9;"),
+            '\n' => escaped.push_str("&#xA;"),
+            '\r' => escaped.push_str("&#xD;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Hash `data` with an XML-DSig digest algorithm, or `None` if the algorithm
+/// is not supported.
+fn digest(algorithm: &str, data: &[u8]) -> Option<Vec<u8>> {
+    let name = algorithm
+        .rsplit_once('#')
+        .map_or(algorithm, |(_, name)| name);
+    Some(match name {
+        "sha1" => Sha1::digest(data).to_vec(),
+        "sha256" => Sha256::digest(data).to_vec(),
+        "sha384" => Sha384::digest(data).to_vec(),
+        "sha512" => Sha512::digest(data).to_vec(),
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::opc::package::SaveOptions;
+    use crate::ooxml::opc::pkgwriter::PackageWriter;
+    use soapberry_zip::office::StreamingArchiveWriter;
+
+    /// Self-signed certificate for `CN=Jane Signer, O=Litchi Test`
+    const CERTIFICATE: &str = "MIIBrzCCAVWgAwIBAgIUFTy8zDH0y1GAmjPmmko3iK2/iOwwCgYIKoZIzj0EAwIwLDEUMBIGA1UEAwwLSmFuZSBTaWduZXIxFDASBgNVBAoMC0xpdGNoaSBUZXN0MCAXDTI2MTAxODA5Mzg0MVoYDzIxMjYwOTI0MDkzODQxWjAsMRQwEgYDVQQDDAtKYW5lIFNpZ25lcjEUMBIGA1UECgwLTGl0Y2hpIFRlc3QwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAASZLLplVh13BYdNZAyK4tAXid4UnowYQCcOZGc/FiVnu0Ngc6u4SHanLGTLSdJ5SMFaGxpnYAKSpjICL/JbiDSlo1MwUTAdBgNVHQ4EFgQU/mlEMpdLCqngH4dk0oaDfZYmVxMwHwYDVR0jBBgwFoAU/mlEMpdLCqngH4dk0oaDfZYmVxMwDwYDVR0TAQH/BAUwAwEB/zAKBggqhkjOPQQDAgNIADBFAiAVIV0QA3V3aDpTGWj/K/edPwMhqd/GjFahOxcH7jwfVwIhAOZvzSQr+ulpOnt6pJ7CJ0uRTvOE87dAkXh+A9CRN63I";
+
+    const DOCUMENT: &[u8] = br#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body><w:p><w:r><w:t>Signed</w:t></w:r></w:p></w:body></w:document>"#;
+
+    /// The package relationships signed through the relationship transform
+    const SIGNED_RELS: &str = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId1" Target="word/document.xml" TargetMode="Internal" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument"></Relationship></Relationships>"#;
+
+    /// Build a .docx package with one signature over the document part and
+    /// its package relationship.
+    fn signed_docx() -> Vec<u8> {
+        let signature = format!(
+            r##"<?xml version="1.0" encoding="UTF-8"?>
+<Signature xmlns="http://www.w3.org/2000/09/xmldsig#" Id="idPackageSignature">
+  <SignedInfo>
+    <CanonicalizationMethod Algorithm="http://www.w3.org/TR/2001/REC-xml-c14n-20010315"/>
+    <SignatureMethod Algorithm="http://www.w3.org/2001/04/xmldsig-more#ecdsa-sha256"/>
+    <Reference Type="http://www.w3.org/2000/09/xmldsig#Object" URI="#idPackageObject">
+      <DigestMethod Algorithm="http://www.w3.org/2001/04/xmlenc#sha256"/>
+      <DigestValue>AAAA</DigestValue>
+    </Reference>
+  </SignedInfo>
+  <SignatureValue>AAAA</SignatureValue>
+  <KeyInfo><X509Data><X509Certificate>{CERTIFICATE}</X509Certificate></X509Data></KeyInfo>
+  <Object Id="idPackageObject">
+    <Manifest>
+      <Reference URI="/_rels/.rels?ContentType=application/vnd.openxmlformats-package.relationships+xml">
+        <Transforms>
+          <Transform Algorithm="http://schemas.openxmlformats.org/package/2006/RelationshipTransform">
+            <mdssi:RelationshipReference xmlns:mdssi="http://schemas.openxmlformats.org/package/2006/digital-signature" SourceId="rId1"/>
+          </Transform>
+          <Transform Algorithm="http://www.w3.org/TR/2001/REC-xml-c14n-20010315"/>
+        </Transforms>
+        <DigestMethod Algorithm="http://www.w3.org/2000/09/xmldsig#sha1"/>
+        <DigestValue>{}</DigestValue>
+      </Reference>
+      <Reference URI="/word/document.xml?ContentType=application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml">
+        <DigestMethod Algorithm="http://www.w3.org/2001/04/xmlenc#sha256"/>
+        <DigestValue>{}</DigestValue>
+      </Reference>
+    </Manifest>
+    <SignatureProperties>
+      <SignatureProperty Id="idSignatureTime" Target="#idPackageSignature">
+        <mdssi:SignatureTime xmlns:mdssi="http://schemas.openxmlformats.org/package/2006/digital-signature">
+          <mdssi:Format>YYYY-MM-DDThh:mm:ssTZD</mdssi:Format>
+          <mdssi:Value>2026-03-01T09:30:00Z</mdssi:Value>
+        </mdssi:SignatureTime>
+      </SignatureProperty>
+    </SignatureProperties>
+  </Object>
+</Signature>"##,
+            BASE64.encode(Sha1::digest(SIGNED_RELS.as_bytes())),
+            BASE64.encode(Sha256::digest(DOCUMENT)),
+        );
+
+        let mut writer = StreamingArchiveWriter::new();
+        writer
+            .write_deflated(
+                "[Content_Types].xml",
+                br#"<?xml version="1.0"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+    <Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+    <Default Extension="xml" ContentType="application/xml"/>
+    <Default Extension="sigs" ContentType="application/vnd.openxmlformats-package.digital-signature-origin"/>
+    <Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+    <Override PartName="/_xmlsignatures/sig1.xml" ContentType="application/vnd.openxmlformats-package.digital-signature-xmlsignature+xml"/>
+</Types>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated(
+                "_rels/.rels",
+                br#"<?xml version="1.0"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+    <Relationship Id="rId2" Type="http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/origin" Target="_xmlsignatures/origin.sigs"/>
+</Relationships>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated("_xmlsignatures/origin.sigs", b"")
+            .unwrap();
+        writer
+            .write_deflated(
+                "_xmlsignatures/_rels/origin.sigs.rels",
+                br#"<?xml version="1.0"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+    <Relationship Id="rId1" Type="http://schemas.openxmlformats.org/package/2006/relationships/digital-signature/signature" Target="sig1.xml"/>
+</Relationships>"#,
+            )
+            .unwrap();
+        writer
+            .write_deflated("_xmlsignatures/sig1.xml", signature.as_bytes())
+            .unwrap();
+        writer
+            .write_deflated("word/document.xml", DOCUMENT)
+            .unwrap();
+        writer.finish_to_bytes().unwrap()
+    }
+
+    #[test]
+    fn test_read_signatures() {
+        let package = OpcPackage::from_bytes(&signed_docx()).unwrap();
+        assert!(package.is_signed());
+
+        let signatures = read_signatures(&package);
+        assert_eq!(signatures.len(), 1);
+        let signature = &signatures[0];
+        assert_eq!(signature.partname, "/_xmlsignatures/sig1.xml");
+        assert_eq!(
+            signature.subject.as_deref(),
+            Some("CN=Jane Signer, O=Litchi Test")
+        );
+        assert_eq!(signature.issuer, signature.subject);
+        assert_eq!(signature.certificates.len(), 1);
+        assert_eq!(
+            signature.signing_time.map(|time| time.to_rfc3339()),
+            Some("2026-03-01T09:30:00+00:00".to_string())
+        );
+        assert_eq!(
+            signature.covered_parts,
+            ["/_rels/.rels", "/word/document.xml"]
+        );
+        assert!(signature.invalid_parts.is_empty());
+        assert!(signature.digests_valid);
+
+        struct RejectAll;
+        impl CertificateValidator for RejectAll {
+            fn validate(&self, _certificates: &[Vec<u8>]) -> bool {
+                false
+            }
+        }
+        assert!(!signature.is_trusted(&RejectAll));
+    }
+
+    #[test]
+    fn test_modified_part_invalidates_digest() {
+        let mut package = OpcPackage::from_bytes(&signed_docx()).unwrap();
+        let document = PackURI::new("/word/document.xml").unwrap();
+        package
+            .get_part_mut(&document)
+            .unwrap()
+            .set_blob(b"<w:document/>".to_vec());
+
+        let signature = &read_signatures(&package)[0];
+        assert!(!signature.digests_valid);
+        assert_eq!(signature.invalid_parts, ["/word/document.xml"]);
+
+        // Unsigned relationships may change without breaking the signature
+        package.relate_to("docProps/core.xml", "urn:example:unsigned");
+        assert_eq!(read_signatures(&package)[0].invalid_parts.len(), 1);
+    }
+
+    #[test]
+    fn test_save_strips_signatures() {
+        let mut package = OpcPackage::from_bytes(&signed_docx()).unwrap();
+        let saved = OpcPackage::from_bytes(&PackageWriter::to_bytes(&package).unwrap()).unwrap();
+        assert!(read_signatures(&saved)[0].digests_valid);
+
+        package.set_save_options(SaveOptions {
+            strip_signatures: true,
+            ..SaveOptions::default()
+        });
+        let stripped = OpcPackage::from_bytes(&PackageWriter::to_bytes(&package).unwrap()).unwrap();
+        assert!(!stripped.is_signed());
+        assert_eq!(stripped.part_count(), 1);
+        assert_eq!(stripped.rels().len(), 1);
+
+        package.remove_signatures();
+        assert!(!package.is_signed());
+        assert_eq!(package.part_count(), 1);
+    }
+}
@@ -3,10 +3,16 @@
 use crate::common::binary;
 use crate::ooxml::xlsb::cell::XlsbCell;
 use crate::ooxml::xlsb::error::XlsbResult;
+use crate::ooxml::xlsb::formula::{FormulaConverter, FormulaParser, FormulaToken, ptg_types};
 use crate::ooxml::xlsb::hyperlinks::Hyperlink;
 use crate::ooxml::xlsb::merged_cells::MergedCell;
 use crate::ooxml::xlsb::records::RecordIter;
-use crate::sheet::CellValue;
+use crate::ooxml::xlsb::rich_string::RichString;
+use crate::ooxml::xlsb::styles_table::StylesTable;
+use crate::ooxml::xlsb::utils::cell_reference;
+use crate::sheet::{CellValue, DateSystem, RowValue};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::io::{Read, Seek};
 
 /// Dimensions of a worksheet
@@ -24,28 +30,97 @@ impl Dimensions {
     }
 }
 
+/// A shared or array formula (BrtShrFmla / BrtArrFmla) and the cells it
+/// covers, 0-based and inclusive
+struct FormulaGroup {
+    first_row: u32,
+    last_row: u32,
+    first_col: u32,
+    last_col: u32,
+    /// Formula tokens
+    rgce: Vec<u8>,
+    is_array: bool,
+}
+
+impl FormulaGroup {
+    /// Parse a BrtShrFmla or BrtArrFmla record
+    ///
+    /// Layout: RfX (4 x u32) + flags (1 byte, BrtArrFmla only) + parsed
+    /// formula (cce u32 + rgce + cb u32 + rgcb).
+    fn parse(typ: u16, buf: &[u8]) -> Option<Self> {
+        let is_array = typ == 0x01AA;
+        let formula_start = if is_array { 17 } else { 16 };
+        let cce = binary::read_u32_le_at(buf, formula_start).ok()? as usize;
+        let rgce = buf.get(formula_start + 4..formula_start + 4 + cce)?;
+        Some(FormulaGroup {
+            first_row: binary::read_u32_le_at(buf, 0).ok()?,
+            last_row: binary::read_u32_le_at(buf, 4).ok()?,
+            first_col: binary::read_u32_le_at(buf, 8).ok()?,
+            last_col: binary::read_u32_le_at(buf, 12).ok()?,
+            rgce: rgce.to_vec(),
+            is_array,
+        })
+    }
+
+    fn contains(&self, row: u32, col: u32) -> bool {
+        (self.first_row..=self.last_row).contains(&row)
+            && (self.first_col..=self.last_col).contains(&col)
+    }
+
+    /// The covered range, such as `A1:C3`
+    fn range(&self) -> String {
+        let first = cell_reference(self.first_row, self.first_col);
+        if (self.first_row, self.first_col) == (self.last_row, self.last_col) {
+            first
+        } else {
+            format!("{}:{}", first, cell_reference(self.last_row, self.last_col))
+        }
+    }
+}
+
+/// A sheet data record decoded by [`XlsbCellsReader::next_event`]
+pub(crate) enum CellsEvent<'a> {
+    /// Start of a row (BrtRowHdr), with its 0-based index
+    Row(u32),
+    /// A cell in the current row, with its 0-based column
+    Cell(u32, RowValue<'a>),
+}
+
 /// XLSB cells reader
 #[allow(dead_code)]
-pub struct XlsbCellsReader<RS>
+pub struct XlsbCellsReader<'a, RS>
 where
     RS: Read + Seek,
 {
     iter: RecordIter<RS>,
-    shared_strings: Vec<String>,
+    shared_strings: &'a [String],
+    extern_sheets: &'a [String],
+    /// Formatted shared strings by index, when collecting rich strings
+    rich_shared_strings: Option<&'a BTreeMap<usize, RichString>>,
+    /// Cell formats and date system for reading date-formatted numbers
+    date_styles: Option<(&'a StylesTable, DateSystem)>,
     dimensions: Dimensions,
     current_row: u32,
     buf: Vec<u8>,
+    /// A record read ahead while looking for the formula of a shared or
+    /// array formula cell
+    pending: Option<(u16, Vec<u8>)>,
+    /// Shared and array formulas read so far
+    formula_groups: Vec<FormulaGroup>,
     /// Merged cells found in the worksheet
     pub merged_cells: Vec<MergedCell>,
     /// Hyperlinks found in the worksheet
     pub hyperlinks: Vec<Hyperlink>,
+    /// Rich strings of the cells read so far, by (row, column), when
+    /// collecting them (see [`Self::with_rich_strings`])
+    pub rich_strings: Vec<((u32, u32), RichString)>,
 }
 
-impl<RS> XlsbCellsReader<RS>
+impl<'a, RS> XlsbCellsReader<'a, RS>
 where
     RS: Read + Seek,
 {
-    pub fn new(mut iter: RecordIter<RS>, shared_strings: Vec<String>) -> XlsbResult<Self> {
+    pub fn new(mut iter: RecordIter<RS>, shared_strings: &'a [String]) -> XlsbResult<Self> {
         let mut buf = Vec::with_capacity(1024);
 
         // Skip to BrtWsDim (worksheet dimensions)
@@ -74,23 +149,108 @@ where
         Ok(XlsbCellsReader {
             iter,
             shared_strings,
+            extern_sheets: &[],
+            rich_shared_strings: None,
+            date_styles: None,
             dimensions,
             current_row: 0,
             buf,
+            pending: None,
+            formula_groups: Vec::new(),
             merged_cells: Vec::new(),
             hyperlinks: Vec::new(),
+            rich_strings: Vec::new(),
         })
     }
 
+    /// Resolve formula references to other sheets through `extern_sheets`,
+    /// the sheet name of each extern sheet table entry.
+    ///
+    /// Without it such references read as `#REF!`.
+    pub fn with_extern_sheets(mut self, extern_sheets: &'a [String]) -> Self {
+        self.extern_sheets = extern_sheets;
+        self
+    }
+
+    /// Collect the formatting runs and phonetic text of rich string cells
+    /// into [`Self::rich_strings`].
+    ///
+    /// `rich_shared_strings` holds the formatted entries of the shared string
+    /// table by index; plain entries are not collected.
+    pub fn with_rich_strings(
+        mut self,
+        rich_shared_strings: &'a BTreeMap<usize, RichString>,
+    ) -> Self {
+        self.rich_shared_strings = Some(rich_shared_strings);
+        self
+    }
+
+    /// Read numbers in cells formatted as dates as dates, using the cell
+    /// formats of `styles` and the workbook's `date_system`.
+    pub fn with_date_styles(mut self, styles: &'a StylesTable, date_system: DateSystem) -> Self {
+        self.date_styles = Some((styles, date_system));
+        self
+    }
+
     #[allow(dead_code)]
     pub fn dimensions(&self) -> Dimensions {
         self.dimensions
     }
 
+    /// Read the next cell, skipping row headers.
     pub fn next_cell(&mut self) -> XlsbResult<Option<XlsbCell>> {
+        loop {
+            match self.next_event()? {
+                Some(CellsEvent::Row(_)) => {},
+                Some(CellsEvent::Cell(col, value)) => {
+                    let value = value.into_owned();
+                    return Ok(Some(if matches!(value, CellValue::Formula { .. }) {
+                        XlsbCell::new_formula(self.current_row, col, value)
+                    } else {
+                        XlsbCell::new(self.current_row, col, value)
+                    }));
+                },
+                None => return Ok(None),
+            }
+        }
+    }
+
+    /// Read the next row header or cell from the sheet data.
+    ///
+    /// Returns `None` after BrtEndSheetData, once the records following the
+    /// sheet data (merged cells, hyperlinks) have been read.
+    pub(crate) fn next_event(&mut self) -> XlsbResult<Option<CellsEvent<'a>>> {
+        match (self.read_event()?, self.date_styles) {
+            (Some(CellsEvent::Cell(col, value)), Some((styles, date_system))) => {
+                // The cell record is still in the buffer: column (u32), then
+                // the 24-bit iStyleRef
+                let style = binary::read_u32_le_at(&self.buf, 4).map_or(0, |s| s & 0x00FF_FFFF);
+                let value = if styles.is_date_style(style as usize) {
+                    value.into_date(date_system)
+                } else {
+                    value
+                };
+                Ok(Some(CellsEvent::Cell(col, value)))
+            },
+            (event, _) => Ok(event),
+        }
+    }
+
+    /// Decode the next row header or cell record
+    fn read_event(&mut self) -> XlsbResult<Option<CellsEvent<'a>>> {
         loop {
             self.buf.clear();
-            let typ = self.iter.read_type()?;
+            let typ = match self.pending.take() {
+                Some((typ, buf)) => {
+                    self.buf = buf;
+                    typ
+                },
+                None => {
+                    let typ = self.iter.read_type()?;
+                    let _ = self.iter.fill_buffer(&mut self.buf)?;
+                    typ
+                },
+            };
 
             if typ == 0x0092 {
                 // BrtEndSheetData - continue to read advanced features
@@ -98,18 +258,17 @@ where
                 return Ok(None);
             }
 
-            let _ = self.iter.fill_buffer(&mut self.buf)?;
-
             match typ {
                 0x0000 => {
                     // BrtRowHdr
                     self.current_row = binary::read_u32_le_at(&self.buf, 0)?;
+                    return Ok(Some(CellsEvent::Row(self.current_row)));
                 },
                 0x0001 => {
                     // BrtCellBlank
                     if self.buf.len() >= 4 {
                         let col = binary::read_u32_le_at(&self.buf, 0)?;
-                        return Ok(Some(XlsbCell::new(self.current_row, col, CellValue::Empty)));
+                        return Ok(Some(CellsEvent::Cell(col, RowValue::Empty)));
                     }
                 },
                 0x0002 => {
@@ -118,29 +277,17 @@ where
                         let col = binary::read_u32_le_at(&self.buf, 0)?;
                         let rk_val = binary::read_u32_le_at(&self.buf, 8)?;
                         let value = Self::parse_rk_value(rk_val);
-                        return Ok(Some(XlsbCell::new(self.current_row, col, value)));
+                        return Ok(Some(CellsEvent::Cell(col, value)));
                     }
                 },
                 0x0003 => {
                     // BrtCellError
                     if self.buf.len() >= 9 {
                         let col = binary::read_u32_le_at(&self.buf, 0)?;
-                        let error_code = self.buf[8];
-                        let error_msg = match error_code {
-                            0x00 => "#NULL!",
-                            0x07 => "#DIV/0!",
-                            0x0F => "#VALUE!",
-                            0x17 => "#REF!",
-                            0x1D => "#NAME?",
-                            0x24 => "#NUM!",
-                            0x2A => "#N/A",
-                            0x2B => "#GETTING_DATA",
-                            _ => "#ERR!",
-                        };
-                        return Ok(Some(XlsbCell::new(
-                            self.current_row,
+                        let error_msg = Self::error_message(self.buf[8]);
+                        return Ok(Some(CellsEvent::Cell(
                             col,
-                            CellValue::Error(error_msg.to_string()),
+                            RowValue::Error(Cow::Borrowed(error_msg)),
                         )));
                     }
                 },
@@ -149,11 +296,7 @@ where
                     if self.buf.len() >= 9 {
                         let col = binary::read_u32_le_at(&self.buf, 0)?;
                         let value = self.buf[8] != 0;
-                        return Ok(Some(XlsbCell::new(
-                            self.current_row,
-                            col,
-                            CellValue::Bool(value),
-                        )));
+                        return Ok(Some(CellsEvent::Cell(col, RowValue::Bool(value))));
                     }
                 },
                 0x0005 => {
@@ -161,11 +304,7 @@ where
                     if self.buf.len() >= 16 {
                         let col = binary::read_u32_le_at(&self.buf, 0)?;
                         let value = binary::read_f64_le_at(&self.buf, 8)?;
-                        return Ok(Some(XlsbCell::new(
-                            self.current_row,
-                            col,
-                            CellValue::Float(value),
-                        )));
+                        return Ok(Some(CellsEvent::Cell(col, RowValue::Float(value))));
                     }
                 },
                 0x0006 => {
@@ -173,10 +312,9 @@ where
                     if self.buf.len() >= 8 {
                         let col = binary::read_u32_le_at(&self.buf, 0)?;
                         let (string, _) = super::records::wide_str_with_len(&self.buf[8..])?;
-                        return Ok(Some(XlsbCell::new(
-                            self.current_row,
+                        return Ok(Some(CellsEvent::Cell(
                             col,
-                            CellValue::String(string),
+                            RowValue::String(Cow::Owned(string)),
                         )));
                     }
                 },
@@ -185,86 +323,43 @@ where
                     if self.buf.len() >= 12 {
                         let col = binary::read_u32_le_at(&self.buf, 0)?;
                         let idx = binary::read_u32_le_at(&self.buf, 8)? as usize;
-                        let value = if idx < self.shared_strings.len() {
-                            CellValue::String(self.shared_strings[idx].clone())
-                        } else {
-                            CellValue::Error("Invalid SST index".to_string())
-                        };
-                        return Ok(Some(XlsbCell::new(self.current_row, col, value)));
-                    }
-                },
-                0x0008 => {
-                    // BrtFmlaString - formula with string result
-                    if self.buf.len() >= 10 {
-                        let col = binary::read_u32_le_at(&self.buf, 0)?;
-                        // Skip style (4 bytes) + flags (1 byte) + formula length (4 bytes)
-                        let formula_len = binary::read_u32_le_at(&self.buf, 6)? as usize;
-                        if self.buf.len() >= 10 + formula_len {
-                            // Read cached string value after formula
-                            let (string, _) =
-                                super::records::wide_str_with_len(&self.buf[10 + formula_len..])?;
-                            return Ok(Some(XlsbCell::new(
-                                self.current_row,
-                                col,
-                                CellValue::String(string),
-                            )));
+                        if let Some(rich) = self.rich_shared_strings.and_then(|map| map.get(&idx)) {
+                            self.rich_strings
+                                .push(((self.current_row, col), rich.clone()));
                         }
+                        let value = match self.shared_strings.get(idx) {
+                            Some(string) => RowValue::String(Cow::Borrowed(string)),
+                            None => RowValue::Error(Cow::Borrowed("Invalid SST index")),
+                        };
+                        return Ok(Some(CellsEvent::Cell(col, value)));
                     }
                 },
-                0x0009 => {
-                    // BrtFmlaNum - formula with numeric result
-                    if self.buf.len() >= 18 {
-                        let col = binary::read_u32_le_at(&self.buf, 0)?;
-                        let formula_len = binary::read_u32_le_at(&self.buf, 6)? as usize;
-                        if self.buf.len() >= 10 + formula_len + 8 {
-                            let num_value = binary::read_f64_le_at(&self.buf, 10 + formula_len)?;
-                            return Ok(Some(XlsbCell::new(
-                                self.current_row,
-                                col,
-                                CellValue::Float(num_value),
-                            )));
-                        }
-                    }
+                0x003E if self.buf.len() >= 8 => {
+                    // BrtCellRString: Cell (8 bytes) + RichStr
+                    let col = binary::read_u32_le_at(&self.buf, 0)?;
+                    let (rich, _) = RichString::parse(&self.buf[8..])?;
+                    let text = if self.rich_shared_strings.is_some() && rich.is_rich() {
+                        let text = rich.text.clone();
+                        self.rich_strings.push(((self.current_row, col), rich));
+                        text
+                    } else {
+                        rich.text
+                    };
+                    return Ok(Some(CellsEvent::Cell(
+                        col,
+                        RowValue::String(Cow::Owned(text)),
+                    )));
                 },
-                0x000A => {
-                    // BrtFmlaBool - formula with boolean result
-                    if self.buf.len() >= 11 {
-                        let col = binary::read_u32_le_at(&self.buf, 0)?;
-                        let formula_len = binary::read_u32_le_at(&self.buf, 6)? as usize;
-                        if self.buf.len() > 10 + formula_len {
-                            let bool_value = self.buf[10 + formula_len] != 0;
-                            return Ok(Some(XlsbCell::new(
-                                self.current_row,
-                                col,
-                                CellValue::Bool(bool_value),
-                            )));
-                        }
+                0x0008..=0x000B => {
+                    // BrtFmlaString / BrtFmlaNum / BrtFmlaBool / BrtFmlaError
+                    if let Some(event) = self.read_formula_cell(typ)? {
+                        return Ok(Some(event));
                     }
                 },
-                0x000B => {
-                    // BrtFmlaError - formula with error result
-                    if self.buf.len() >= 11 {
-                        let col = binary::read_u32_le_at(&self.buf, 0)?;
-                        let formula_len = binary::read_u32_le_at(&self.buf, 6)? as usize;
-                        if self.buf.len() > 10 + formula_len {
-                            let error_code = self.buf[10 + formula_len];
-                            let error_msg = match error_code {
-                                0x00 => "#NULL!",
-                                0x07 => "#DIV/0!",
-                                0x0F => "#VALUE!",
-                                0x17 => "#REF!",
-                                0x1D => "#NAME?",
-                                0x24 => "#NUM!",
-                                0x2A => "#N/A",
-                                0x2B => "#GETTING_DATA",
-                                _ => "#ERR!",
-                            };
-                            return Ok(Some(XlsbCell::new(
-                                self.current_row,
-                                col,
-                                CellValue::Error(error_msg.to_string()),
-                            )));
-                        }
+                0x01AA | 0x01AB => {
+                    // BrtArrFmla / BrtShrFmla
+                    if let Some(group) = FormulaGroup::parse(typ, &self.buf) {
+                        self.formula_groups.push(group);
                     }
                 },
                 _ => {
@@ -274,6 +369,155 @@ where
         }
     }
 
+    /// Decode a formula cell record
+    ///
+    /// Layout: Cell (8 bytes) + cached value + grbitFlags (2 bytes) +
+    /// CellParsedFormula (cce u32 + rgce + cb u32 + rgcb). If the formula
+    /// cannot be decoded, the cell reads as its cached value.
+    fn read_formula_cell(&mut self, typ: u16) -> XlsbResult<Option<CellsEvent<'a>>> {
+        let buf = &self.buf;
+        if buf.len() < 9 {
+            return Ok(None);
+        }
+        let col = binary::read_u32_le_at(buf, 0)?;
+
+        let (cached_value, value_len) = match typ {
+            0x0008 => {
+                let (string, len) = super::records::wide_str_with_len(&buf[8..])?;
+                (RowValue::String(Cow::Owned(string)), len)
+            },
+            0x0009 => {
+                if buf.len() < 16 {
+                    return Ok(None);
+                }
+                (RowValue::Float(binary::read_f64_le_at(buf, 8)?), 8)
+            },
+            0x000A => (RowValue::Bool(buf[8] != 0), 1),
+            _ => (
+                RowValue::Error(Cow::Borrowed(Self::error_message(buf[8]))),
+                1,
+            ),
+        };
+
+        let formula_start = 8 + value_len + 2;
+        let rgce = binary::read_u32_le_at(buf, formula_start)
+            .ok()
+            .and_then(|cce| buf.get(formula_start + 4..formula_start + 4 + cce as usize));
+
+        // Cells of shared and array formulas only point at the formula
+        if rgce.is_some_and(|rgce| rgce.first() == Some(&ptg_types::PTG_EXP)) {
+            return self.read_grouped_cell(col, cached_value);
+        }
+
+        let formula = rgce.and_then(|rgce| self.formula_text(FormulaParser::new(rgce)));
+        let value = match formula {
+            Some(formula) => RowValue::Formula {
+                formula: Cow::Owned(formula),
+                cached_value: Some(Box::new(cached_value)),
+                is_array: false,
+                array_range: None,
+            },
+            None => cached_value,
+        };
+        Ok(Some(CellsEvent::Cell(col, value)))
+    }
+
+    /// Resolve a cell that is part of a shared or array formula
+    ///
+    /// The BrtShrFmla or BrtArrFmla record follows the first cell of its
+    /// range, so it is read ahead when the cell is not covered by a formula
+    /// seen before. Cells of a shared formula get the formula with relative
+    /// references resolved for their position. Array formulas are reported
+    /// on their first cell only, like in XLSX; the other cells read as their
+    /// cached values.
+    fn read_grouped_cell(
+        &mut self,
+        col: u32,
+        cached_value: RowValue<'a>,
+    ) -> XlsbResult<Option<CellsEvent<'a>>> {
+        let row = self.current_row;
+        if !self
+            .formula_groups
+            .iter()
+            .any(|group| group.contains(row, col))
+        {
+            let mut buf = Vec::new();
+            let typ = self.iter.read_type()?;
+            let _ = self.iter.fill_buffer(&mut buf)?;
+            match FormulaGroup::parse(typ, &buf).filter(|_| matches!(typ, 0x01AA | 0x01AB)) {
+                Some(group) => self.formula_groups.push(group),
+                None => self.pending = Some((typ, buf)),
+            }
+        }
+
+        let Some(group) = self
+            .formula_groups
+            .iter()
+            .rev()
+            .find(|group| group.contains(row, col))
+        else {
+            return Ok(Some(CellsEvent::Cell(col, cached_value)));
+        };
+
+        let (formula, array_range) = if group.is_array {
+            if (row, col) != (group.first_row, group.first_col) {
+                return Ok(Some(CellsEvent::Cell(col, cached_value)));
+            }
+            (
+                self.formula_text(FormulaParser::new(&group.rgce)),
+                Some(Cow::Owned(group.range())),
+            )
+        } else {
+            (
+                self.formula_text(FormulaParser::new(&group.rgce).with_base(row, col)),
+                None,
+            )
+        };
+
+        let value = match formula {
+            Some(formula) => RowValue::Formula {
+                formula: Cow::Owned(formula),
+                cached_value: Some(Box::new(cached_value)),
+                is_array: group.is_array,
+                array_range,
+            },
+            None => cached_value,
+        };
+        Ok(Some(CellsEvent::Cell(col, value)))
+    }
+
+    /// Decode formula tokens, `None` if they contain a token that cannot be
+    /// decoded
+    fn formula_text(&self, mut parser: FormulaParser) -> Option<String> {
+        parser
+            .parse()
+            .ok()
+            .filter(|tokens| {
+                !tokens.is_empty()
+                    && !tokens
+                        .iter()
+                        .any(|token| matches!(token, FormulaToken::Unknown(_)))
+            })
+            .map(|tokens| {
+                FormulaConverter::tokens_to_string_with_sheets(&tokens, self.extern_sheets)
+            })
+    }
+
+    /// Formula text of an error code (BErr)
+    fn error_message(code: u8) -> &'static str {
+        match code {
+            0x00 => "#NULL!",
+            0x07 => "#DIV/0!",
+            0x0F => "#VALUE!",
+            0x17 => "#REF!",
+            0x1D => "#NAME?",
+            0x24 => "#NUM!",
+            0x2A => "#N/A",
+            0x2B => "#GETTING_DATA",
+            _ => "#ERR!",
+        }
+    }
+
     fn parse_dimensions(buf: &[u8]) -> Dimensions {
         Dimensions {
             start: (
@@ -287,7 +531,7 @@ where
         }
     }
 
-    fn parse_rk_value(rk: u32) -> CellValue {
+    fn parse_rk_value(rk: u32) -> RowValue<'a> {
         let d100 = (rk & 0x02) != 0;
         let is_int = (rk & 0x01) != 0;
 
@@ -302,7 +546,7 @@ where
             } else {
                 int_val as f64
             };
-            CellValue::Int(value as i64)
+            RowValue::Int(value as i64)
         } else {
             let mut float_bits = [0u8; 8];
             let masked_rk = rk & 0xFFFFFFFC;
@@ -314,9 +558,9 @@ where
 
             // Check if it's a whole number
             if value == value.round() && value >= i64::MIN as f64 && value <= i64::MAX as f64 {
-                CellValue::Int(value as i64)
+                RowValue::Int(value as i64)
             } else {
-                CellValue::Float(value)
+                RowValue::Float(value)
             }
         }
     }
@@ -372,14 +616,13 @@ where
         loop {
             self.buf.clear();
             let typ = self.iter.read_type()?;
+            let _ = self.iter.fill_buffer(&mut self.buf)?;
 
             if typ == 0x00B2 {
                 // BrtEndMergeCells
                 break;
             }
 
-            let _ = self.iter.fill_buffer(&mut self.buf)?;
-
             if typ == 0x00B0 {
                 // BrtMergeCell
                 if let Ok(merged) = MergedCell::parse(&self.buf) {
@@ -391,3 +634,97 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::xlsb::writer::RecordWriter;
+    use crate::sheet::Cell;
+    use std::io::Cursor;
+
+    /// BrtFmlaNum whose formula only points at a shared or array formula
+    fn grouped_cell(col: u32, value: f64) -> Vec<u8> {
+        let mut data = Vec::new();
+        data.extend_from_slice(&col.to_le_bytes());
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&value.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data.extend_from_slice(&5u32.to_le_bytes());
+        data.extend_from_slice(&[ptg_types::PTG_EXP, 0, 0, 0, 0]);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data
+    }
+
+    /// BrtShrFmla or BrtArrFmla over `rows` x `cols`
+    fn formula_group(typ: u16, rows: (u32, u32), cols: (u32, u32), rgce: &[u8]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for value in [rows.0, rows.1, cols.0, cols.1] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        if typ == 0x01AA {
+            data.push(0);
+        }
+        data.extend_from_slice(&(rgce.len() as u32).to_le_bytes());
+        data.extend_from_slice(rgce);
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn test_shared_and_array_formulas() {
+        // B2:B3 share `B1+1`, C3:C4 hold the array formula `$A$1`
+        let shared = [0x2C, 0xFF, 0xFF, 0xFF, 0xFF, 0x00, 0xC0, 0x1E, 1, 0, 0x03];
+        let array = [0x24, 0, 0, 0, 0, 0, 0];
+        let mut real = 1u32.to_le_bytes().to_vec();
+        real.extend_from_slice(&[0; 4]);
+        real.extend_from_slice(&2.5f64.to_le_bytes());
+
+        let mut data = Vec::new();
+        let mut writer = RecordWriter::new(&mut data);
+        let records: [(u16, Vec<u8>); 15] = [
+            (0x0094, vec![0; 16]),
+            (0x0091, Vec::new()),
+            (0x0000, 1u32.to_le_bytes().to_vec()),
+            (0x0009, grouped_cell(1, 2.0)),
+            (0x01AB, formula_group(0x01AB, (1, 2), (1, 1), &shared)),
+            (0x0000, 2u32.to_le_bytes().to_vec()),
+            (0x0009, grouped_cell(1, 3.0)),
+            (0x0009, grouped_cell(2, 7.0)),
+            (0x01AA, formula_group(0x01AA, (2, 3), (2, 2), &array)),
+            (0x0000, 3u32.to_le_bytes().to_vec()),
+            (0x0009, grouped_cell(2, 7.0)),
+            // A cell whose formula is missing keeps its cached value
+            (0x0000, 4u32.to_le_bytes().to_vec()),
+            (0x0009, grouped_cell(0, 1.5)),
+            (0x0005, real),
+            (0x0092, Vec::new()),
+        ];
+        for (typ, record) in &records {
+            writer.write_record(*typ, record).unwrap();
+        }
+
+        let mut reader = XlsbCellsReader::new(RecordIter::new(Cursor::new(data)), &[]).unwrap();
+        let mut cells = Vec::new();
+        while let Some(cell) = reader.next_cell().unwrap() {
+            cells.push((cell.coordinate(), cell.value().clone()));
+        }
+
+        let formula = |formula: &str, value: f64, array_range: Option<&str>| CellValue::Formula {
+            formula: formula.to_string(),
+            cached_value: Some(Box::new(CellValue::Float(value))),
+            is_array: array_range.is_some(),
+            array_range: array_range.map(str::to_string),
+        };
+        assert_eq!(
+            cells,
+            vec![
+                ("B2".to_string(), formula("B1+1", 2.0, None)),
+                ("B3".to_string(), formula("B2+1", 3.0, None)),
+                ("C3".to_string(), formula("$A$1", 7.0, Some("C3:C4"))),
+                ("C4".to_string(), CellValue::Float(7.0)),
+                ("A5".to_string(), CellValue::Float(1.5)),
+                ("B5".to_string(), CellValue::Float(2.5)),
+            ]
+        );
+    }
+}
@@ -166,6 +166,9 @@ impl From<crate::ole::OleError> for XlsbError {
             crate::ole::OleError::CorruptedFile(msg) => {
                 XlsbError::Encoding(format!("Corrupted file: {}", msg))
             },
+            crate::ole::OleError::ParseError(msg) => {
+                XlsbError::Encoding(format!("Parse error: {}", msg))
+            },
             crate::ole::OleError::StreamNotFound => {
                 XlsbError::FileNotFound("Stream not found".to_string())
             },
@@ -188,6 +191,9 @@ impl From<crate::ooxml::error::OoxmlError> for XlsbError {
             crate::ooxml::error::OoxmlError::Xml(msg) => {
                 XlsbError::Encoding(format!("XML error: {}", msg))
             },
+            crate::ooxml::error::OoxmlError::MalformedXml { .. } => {
+                XlsbError::Encoding(err.to_string())
+            },
             crate::ooxml::error::OoxmlError::PartNotFound(path) => XlsbError::FileNotFound(path),
             crate::ooxml::error::OoxmlError::InvalidContentType { expected, got } => {
                 XlsbError::Encoding(format!(
@@ -204,7 +210,9 @@ impl From<crate::ooxml::error::OoxmlError> for XlsbError {
             },
             crate::ooxml::error::OoxmlError::InvalidFormat(msg) => XlsbError::Encoding(msg),
             crate::ooxml::error::OoxmlError::Io(e) => XlsbError::Io(e),
-            crate::ooxml::error::OoxmlError::Other(msg) => XlsbError::Encoding(msg),
+            crate::ooxml::error::OoxmlError::IncorrectPassword(msg)
+            | crate::ooxml::error::OoxmlError::UnsupportedEncryption(msg)
+            | crate::ooxml::error::OoxmlError::Other(msg) => XlsbError::Encoding(msg),
         }
     }
 }
@@ -1,7 +1,9 @@
 //! XLSB formula parsing and generation
 //!
 //! Excel formulas in XLSB files are stored in a binary format using Reverse Polish Notation (RPN)
-//! with Parse Tree Generators (Ptg tokens). This module provides parsing and generation of formulas.
+//! with Parse Tree Generators (Ptg tokens). This module decodes formula bytes into tokens and
+//! formula text ([`FormulaParser`], [`FormulaConverter`]) and compiles formula text into formula
+//! bytes ([`FormulaEncoder`]).
 //!
 //! # Formula Token Types (Ptgs)
 //!
@@ -23,7 +25,7 @@
 //! - [MS-XLS] Section 2.5.198 - Ptg (for token details, largely compatible)
 
 use crate::common::binary;
-use crate::ooxml::xlsb::error::XlsbResult;
+use crate::ooxml::xlsb::error::{XlsbError, XlsbResult};
 
 /// Parse Tree Generator (Ptg) token types
 ///
@@ -89,12 +91,51 @@ pub mod ptg_types {
     // Array and name
     pub const PTG_NAME: u8 = 0x23; // Defined name
     pub const PTG_ARRAY: u8 = 0x20; // Array constant
+
+    // Classes, added to the reference-class Ptgs above (0x20-0x3F)
+    pub const PTG_CLASS_REFERENCE: u8 = 0x00;
+    pub const PTG_CLASS_VALUE: u8 = 0x20;
+    pub const PTG_CLASS_ARRAY: u8 = 0x40;
+
+    // PtgAttr subtypes
+    pub const ATTR_SEMI: u8 = 0x01; // Volatile formula
+    pub const ATTR_IF: u8 = 0x02; // IF jump
+    pub const ATTR_CHOOSE: u8 = 0x04; // CHOOSE jump table
+    pub const ATTR_GOTO: u8 = 0x08; // Jump
+    pub const ATTR_SUM: u8 = 0x10; // SUM with a single argument
+    pub const ATTR_SPACE: u8 = 0x40; // Whitespace
 }
 
+/// Largest 0-based row index of a worksheet
+const MAX_ROW: u32 = 1_048_575;
+/// Largest 0-based column index of a worksheet
+const MAX_COL: u32 = 16_383;
+/// Longest string constant a formula can hold, in UTF-16 code units
+const MAX_STRING_LEN: usize = 255;
+
+/// Extern sheet index (`ixti`) of the first worksheet in files written by
+/// [`XlsbWorkbookWriter`](crate::ooxml::xlsb::writer::XlsbWorkbookWriter).
+///
+/// Its extern sheet table starts with a workbook-level entry and a `#REF!`
+/// entry, followed by one entry per worksheet.
+pub(crate) const FIRST_SHEET_IXTI: u16 = 2;
+
+/// Error codes and their formula text
+const ERROR_CODES: [(u8, &str); 8] = [
+    (0x00, "#NULL!"),
+    (0x07, "#DIV/0!"),
+    (0x0F, "#VALUE!"),
+    (0x17, "#REF!"),
+    (0x1D, "#NAME?"),
+    (0x24, "#NUM!"),
+    (0x2A, "#N/A"),
+    (0x2B, "#GETTING_DATA"),
+];
+
 /// Formula token representation
 ///
 /// Represents a single token in a formula's RPN sequence.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum FormulaToken {
     /// Number constant
     Number(f64),
@@ -124,10 +165,34 @@ pub enum FormulaToken {
         col_first_relative: bool,
         col_last_relative: bool,
     },
+    /// Cell reference on another sheet, through the extern sheet table entry `ixti`
+    CellRef3d {
+        ixti: u16,
+        row: u32,
+        col: u32,
+        row_relative: bool,
+        col_relative: bool,
+    },
+    /// Area reference on another sheet, through the extern sheet table entry `ixti`
+    AreaRef3d {
+        ixti: u16,
+        row_first: u32,
+        row_last: u32,
+        col_first: u32,
+        col_last: u32,
+        row_first_relative: bool,
+        row_last_relative: bool,
+        col_first_relative: bool,
+        col_last_relative: bool,
+    },
     /// Binary operator
     BinaryOp(BinaryOperator),
     /// Unary operator
     UnaryOp(UnaryOperator),
+    /// Parentheses around the preceding expression
+    Paren,
+    /// Omitted function argument
+    MissingArg,
     /// Function call (function index, arg count)
     Function { index: u16, arg_count: u8 },
     /// Defined name reference
@@ -151,6 +216,42 @@ pub enum BinaryOperator {
     GreaterEqual,
     GreaterThan,
     NotEqual,
+    Intersect,
+    Union,
+    Range,
+}
+
+impl BinaryOperator {
+    /// The Ptg that applies this operator
+    fn ptg(self) -> u8 {
+        use ptg_types::*;
+
+        match self {
+            BinaryOperator::Add => PTG_ADD,
+            BinaryOperator::Subtract => PTG_SUB,
+            BinaryOperator::Multiply => PTG_MUL,
+            BinaryOperator::Divide => PTG_DIV,
+            BinaryOperator::Power => PTG_POWER,
+            BinaryOperator::Concat => PTG_CONCAT,
+            BinaryOperator::LessThan => PTG_LT,
+            BinaryOperator::LessEqual => PTG_LE,
+            BinaryOperator::Equal => PTG_EQ,
+            BinaryOperator::GreaterEqual => PTG_GE,
+            BinaryOperator::GreaterThan => PTG_GT,
+            BinaryOperator::NotEqual => PTG_NE,
+            BinaryOperator::Intersect => PTG_ISECT,
+            BinaryOperator::Union => PTG_UNION,
+            BinaryOperator::Range => PTG_RANGE,
+        }
+    }
+
+    /// Whether the operator combines references rather than values
+    fn is_reference_operator(self) -> bool {
+        matches!(
+            self,
+            BinaryOperator::Intersect | BinaryOperator::Union | BinaryOperator::Range
+        )
+    }
 }
 
 /// Unary operators
@@ -161,32 +262,274 @@ pub enum UnaryOperator {
     Percent,
 }
 
+impl UnaryOperator {
+    /// The Ptg that applies this operator
+    fn ptg(self) -> u8 {
+        match self {
+            UnaryOperator::Plus => ptg_types::PTG_UPLUS,
+            UnaryOperator::Minus => ptg_types::PTG_UMINUS,
+            UnaryOperator::Percent => ptg_types::PTG_PERCENT,
+        }
+    }
+}
+
+/// A built-in function from the function table (Ftab)
+///
+/// Functions that always take the same number of arguments are stored as
+/// `PtgFunc`; the others as `PtgFuncVar`, which records the argument count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionInfo {
+    /// Index in the function table
+    pub index: u16,
+    /// Function name as written in formulas
+    pub name: &'static str,
+    /// Minimum number of arguments
+    pub min_args: u8,
+    /// Maximum number of arguments
+    pub max_args: u8,
+}
+
+impl FunctionInfo {
+    /// Look up a built-in function by name (case-insensitive)
+    pub fn by_name(name: &str) -> Option<&'static FunctionInfo> {
+        FUNCTIONS.iter().find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Look up a built-in function by its function table index
+    pub fn by_index(index: u16) -> Option<&'static FunctionInfo> {
+        FUNCTIONS.iter().find(|f| f.index == index)
+    }
+
+    /// Whether the function always takes the same number of arguments
+    pub fn has_fixed_args(&self) -> bool {
+        self.min_args == self.max_args
+    }
+}
+
+const fn func(index: u16, name: &'static str, min_args: u8, max_args: u8) -> FunctionInfo {
+    FunctionInfo {
+        index,
+        name,
+        min_args,
+        max_args,
+    }
+}
+
+/// Built-in functions ([MS-XLS] 2.5.198.17 Ftab)
+static FUNCTIONS: &[FunctionInfo] = &[
+    func(0, "COUNT", 1, 255),
+    func(1, "IF", 2, 3),
+    func(2, "ISNA", 1, 1),
+    func(3, "ISERROR", 1, 1),
+    func(4, "SUM", 1, 255),
+    func(5, "AVERAGE", 1, 255),
+    func(6, "MIN", 1, 255),
+    func(7, "MAX", 1, 255),
+    func(8, "ROW", 0, 1),
+    func(9, "COLUMN", 0, 1),
+    func(10, "NA", 0, 0),
+    func(11, "NPV", 2, 255),
+    func(12, "STDEV", 1, 255),
+    func(13, "DOLLAR", 1, 2),
+    func(14, "FIXED", 1, 3),
+    func(15, "SIN", 1, 1),
+    func(16, "COS", 1, 1),
+    func(17, "TAN", 1, 1),
+    func(18, "ATAN", 1, 1),
+    func(19, "PI", 0, 0),
+    func(20, "SQRT", 1, 1),
+    func(21, "EXP", 1, 1),
+    func(22, "LN", 1, 1),
+    func(23, "LOG10", 1, 1),
+    func(24, "ABS", 1, 1),
+    func(25, "INT", 1, 1),
+    func(26, "SIGN", 1, 1),
+    func(27, "ROUND", 2, 2),
+    func(28, "LOOKUP", 2, 3),
+    func(29, "INDEX", 2, 4),
+    func(30, "REPT", 2, 2),
+    func(31, "MID", 3, 3),
+    func(32, "LEN", 1, 1),
+    func(33, "VALUE", 1, 1),
+    func(34, "TRUE", 0, 0),
+    func(35, "FALSE", 0, 0),
+    func(36, "AND", 1, 255),
+    func(37, "OR", 1, 255),
+    func(38, "NOT", 1, 1),
+    func(39, "MOD", 2, 2),
+    func(46, "VAR", 1, 255),
+    func(48, "TEXT", 2, 2),
+    func(56, "PV", 3, 5),
+    func(57, "FV", 3, 5),
+    func(58, "NPER", 3, 5),
+    func(59, "PMT", 3, 5),
+    func(60, "RATE", 3, 6),
+    func(61, "MIRR", 3, 3),
+    func(62, "IRR", 1, 2),
+    func(63, "RAND", 0, 0),
+    func(64, "MATCH", 2, 3),
+    func(65, "DATE", 3, 3),
+    func(66, "TIME", 3, 3),
+    func(67, "DAY", 1, 1),
+    func(68, "MONTH", 1, 1),
+    func(69, "YEAR", 1, 1),
+    func(70, "WEEKDAY", 1, 2),
+    func(71, "HOUR", 1, 1),
+    func(72, "MINUTE", 1, 1),
+    func(73, "SECOND", 1, 1),
+    func(74, "NOW", 0, 0),
+    func(75, "AREAS", 1, 1),
+    func(76, "ROWS", 1, 1),
+    func(77, "COLUMNS", 1, 1),
+    func(78, "OFFSET", 3, 5),
+    func(82, "SEARCH", 2, 3),
+    func(83, "TRANSPOSE", 1, 1),
+    func(86, "TYPE", 1, 1),
+    func(97, "ATAN2", 2, 2),
+    func(98, "ASIN", 1, 1),
+    func(99, "ACOS", 1, 1),
+    func(100, "CHOOSE", 2, 255),
+    func(101, "HLOOKUP", 3, 4),
+    func(102, "VLOOKUP", 3, 4),
+    func(105, "ISREF", 1, 1),
+    func(109, "LOG", 1, 2),
+    func(111, "CHAR", 1, 1),
+    func(112, "LOWER", 1, 1),
+    func(113, "UPPER", 1, 1),
+    func(114, "PROPER", 1, 1),
+    func(115, "LEFT", 1, 2),
+    func(116, "RIGHT", 1, 2),
+    func(117, "EXACT", 2, 2),
+    func(118, "TRIM", 1, 1),
+    func(119, "REPLACE", 4, 4),
+    func(120, "SUBSTITUTE", 3, 4),
+    func(121, "CODE", 1, 1),
+    func(124, "FIND", 2, 3),
+    func(125, "CELL", 1, 2),
+    func(126, "ISERR", 1, 1),
+    func(127, "ISTEXT", 1, 1),
+    func(128, "ISNUMBER", 1, 1),
+    func(129, "ISBLANK", 1, 1),
+    func(130, "T", 1, 1),
+    func(131, "N", 1, 1),
+    func(140, "DATEVALUE", 1, 1),
+    func(141, "TIMEVALUE", 1, 1),
+    func(142, "SLN", 3, 3),
+    func(143, "SYD", 4, 4),
+    func(144, "DDB", 4, 5),
+    func(148, "INDIRECT", 1, 2),
+    func(162, "CLEAN", 1, 1),
+    func(163, "MDETERM", 1, 1),
+    func(164, "MINVERSE", 1, 1),
+    func(165, "MMULT", 2, 2),
+    func(167, "IPMT", 4, 6),
+    func(168, "PPMT", 4, 6),
+    func(169, "COUNTA", 1, 255),
+    func(183, "PRODUCT", 1, 255),
+    func(184, "FACT", 1, 1),
+    func(190, "ISNONTEXT", 1, 1),
+    func(193, "STDEVP", 1, 255),
+    func(194, "VARP", 1, 255),
+    func(197, "TRUNC", 1, 2),
+    func(198, "ISLOGICAL", 1, 1),
+    func(212, "ROUNDUP", 2, 2),
+    func(213, "ROUNDDOWN", 2, 2),
+    func(216, "RANK", 2, 3),
+    func(219, "ADDRESS", 2, 5),
+    func(220, "DAYS360", 2, 3),
+    func(221, "TODAY", 0, 0),
+    func(227, "MEDIAN", 1, 255),
+    func(228, "SUMPRODUCT", 1, 255),
+    func(229, "SINH", 1, 1),
+    func(230, "COSH", 1, 1),
+    func(231, "TANH", 1, 1),
+    func(244, "INFO", 1, 1),
+    func(261, "ERROR.TYPE", 1, 1),
+    func(276, "COMBIN", 2, 2),
+    func(279, "EVEN", 1, 1),
+    func(285, "FLOOR", 2, 2),
+    func(288, "CEILING", 2, 2),
+    func(293, "NORMDIST", 4, 4),
+    func(298, "ODD", 1, 1),
+    func(299, "PERMUT", 2, 2),
+    func(307, "CORREL", 2, 2),
+    func(309, "FORECAST", 3, 3),
+    func(311, "INTERCEPT", 2, 2),
+    func(315, "SLOPE", 2, 2),
+    func(321, "SUMSQ", 1, 255),
+    func(325, "LARGE", 2, 2),
+    func(326, "SMALL", 2, 2),
+    func(327, "QUARTILE", 2, 2),
+    func(328, "PERCENTILE", 2, 2),
+    func(330, "MODE", 1, 255),
+    func(336, "CONCATENATE", 1, 255),
+    func(337, "POWER", 2, 2),
+    func(342, "RADIANS", 1, 1),
+    func(343, "DEGREES", 1, 1),
+    func(344, "SUBTOTAL", 2, 255),
+    func(345, "SUMIF", 2, 3),
+    func(346, "COUNTIF", 2, 2),
+    func(347, "COUNTBLANK", 1, 1),
+    func(354, "ROMAN", 1, 2),
+    func(359, "HYPERLINK", 1, 2),
+    func(361, "AVERAGEA", 1, 255),
+    func(362, "MAXA", 1, 255),
+    func(363, "MINA", 1, 255),
+    func(480, "IFERROR", 2, 2),
+    func(481, "COUNTIFS", 2, 254),
+    func(482, "SUMIFS", 3, 255),
+    func(483, "AVERAGEIF", 2, 3),
+    func(484, "AVERAGEIFS", 3, 255),
+];
+
 /// Formula parser
 ///
-/// Parses binary formula bytes into a sequence of tokens.
+/// Parses binary formula bytes (`rgce`) into a sequence of tokens.
 pub struct FormulaParser<'a> {
     data: &'a [u8],
     offset: usize,
+    /// Cell that relative references (`PtgRefN`, `PtgAreaN`) are resolved
+    /// against, as 0-based row and column
+    base: Option<(u32, u32)>,
 }
 
 impl<'a> FormulaParser<'a> {
     /// Create a new formula parser
     pub fn new(data: &'a [u8]) -> Self {
-        FormulaParser { data, offset: 0 }
+        FormulaParser {
+            data,
+            offset: 0,
+            base: None,
+        }
+    }
+
+    /// Resolve the relative references of a shared formula for the cell at
+    /// `row` and `col` (0-based).
+    ///
+    /// Shared formulas store the relative parts of their references as
+    /// offsets from the cell that uses them, which Excel wraps around the
+    /// edges of the sheet.
+    pub fn with_base(mut self, row: u32, col: u32) -> Self {
+        self.base = Some((row, col));
+        self
     }
 
     /// Parse the formula into tokens
     ///
-    /// Returns a vector of formula tokens in RPN order.
+    /// Returns a vector of formula tokens in RPN order. Tokens that only
+    /// matter to Excel's calculation engine (`PtgAttr` jumps and spaces,
+    /// `PtgMem*` headers) are skipped. Parsing stops after the first
+    /// [`FormulaToken::Unknown`], as the size of its data is not known.
     pub fn parse(&mut self) -> XlsbResult<Vec<FormulaToken>> {
         let mut tokens = Vec::new();
 
         while self.offset < self.data.len() {
             if let Some(token) = self.parse_token()? {
+                let unknown = matches!(token, FormulaToken::Unknown(_));
                 tokens.push(token);
-            } else {
-                // Skip unknown tokens
-                self.offset += 1;
+                if unknown {
+                    break;
+                }
             }
         }
 
@@ -195,254 +538,319 @@ impl<'a> FormulaParser<'a> {
 
     /// Parse a single token
     fn parse_token(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset >= self.data.len() {
-            return Ok(None);
-        }
-
-        let ptg_type = self.data[self.offset];
-        self.offset += 1;
-
         use ptg_types::*;
 
-        match ptg_type {
-            PTG_ADD => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::Add))),
-            PTG_SUB => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::Subtract))),
-            PTG_MUL => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::Multiply))),
-            PTG_DIV => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::Divide))),
-            PTG_POWER => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::Power))),
-            PTG_CONCAT => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::Concat))),
-            PTG_LT => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::LessThan))),
-            PTG_LE => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::LessEqual))),
-            PTG_EQ => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::Equal))),
-            PTG_GE => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::GreaterEqual))),
-            PTG_GT => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::GreaterThan))),
-            PTG_NE => Ok(Some(FormulaToken::BinaryOp(BinaryOperator::NotEqual))),
-
-            PTG_UPLUS => Ok(Some(FormulaToken::UnaryOp(UnaryOperator::Plus))),
-            PTG_UMINUS => Ok(Some(FormulaToken::UnaryOp(UnaryOperator::Minus))),
-            PTG_PERCENT => Ok(Some(FormulaToken::UnaryOp(UnaryOperator::Percent))),
-
-            PTG_INT => self.parse_int(),
-            PTG_NUM => self.parse_num(),
-            PTG_STR => self.parse_str(),
-            PTG_BOOL => self.parse_bool(),
-            PTG_ERR => self.parse_err(),
-
-            PTG_REF | PTG_REF_N => self.parse_ref(),
-            PTG_AREA | PTG_AREA_N => self.parse_area(),
-
-            PTG_FUNC => self.parse_func(),
-            PTG_FUNC_VAR => self.parse_func_var(),
-
-            PTG_NAME => self.parse_name(),
-
-            _ => {
-                // Unknown token type
-                Ok(Some(FormulaToken::Unknown(ptg_type)))
+        let ptg_type = self.read_u8()?;
+
+        // Operand and function Ptgs come in reference (0x20), value (0x40)
+        // and array (0x60) classes; fold them onto the reference class.
+        let base_type = if ptg_type & 0x60 != 0 {
+            (ptg_type & 0x1F) | 0x20
+        } else {
+            ptg_type
+        };
+
+        let token = match base_type {
+            PTG_ADD => FormulaToken::BinaryOp(BinaryOperator::Add),
+            PTG_SUB => FormulaToken::BinaryOp(BinaryOperator::Subtract),
+            PTG_MUL => FormulaToken::BinaryOp(BinaryOperator::Multiply),
+            PTG_DIV => FormulaToken::BinaryOp(BinaryOperator::Divide),
+            PTG_POWER => FormulaToken::BinaryOp(BinaryOperator::Power),
+            PTG_CONCAT => FormulaToken::BinaryOp(BinaryOperator::Concat),
+            PTG_LT => FormulaToken::BinaryOp(BinaryOperator::LessThan),
+            PTG_LE => FormulaToken::BinaryOp(BinaryOperator::LessEqual),
+            PTG_EQ => FormulaToken::BinaryOp(BinaryOperator::Equal),
+            PTG_GE => FormulaToken::BinaryOp(BinaryOperator::GreaterEqual),
+            PTG_GT => FormulaToken::BinaryOp(BinaryOperator::GreaterThan),
+            PTG_NE => FormulaToken::BinaryOp(BinaryOperator::NotEqual),
+            PTG_ISECT => FormulaToken::BinaryOp(BinaryOperator::Intersect),
+            PTG_UNION => FormulaToken::BinaryOp(BinaryOperator::Union),
+            PTG_RANGE => FormulaToken::BinaryOp(BinaryOperator::Range),
+
+            PTG_UPLUS => FormulaToken::UnaryOp(UnaryOperator::Plus),
+            PTG_UMINUS => FormulaToken::UnaryOp(UnaryOperator::Minus),
+            PTG_PERCENT => FormulaToken::UnaryOp(UnaryOperator::Percent),
+            PTG_PAREN => FormulaToken::Paren,
+            PTG_MISSING_ARG => FormulaToken::MissingArg,
+
+            PTG_INT => FormulaToken::Int(self.read_u16()?),
+            PTG_NUM => FormulaToken::Number(binary::read_f64_le_at(self.take(8)?, 0)?),
+            PTG_STR => self.parse_str()?,
+            PTG_BOOL => FormulaToken::Bool(self.read_u8()? != 0),
+            PTG_ERR => FormulaToken::Error(self.read_u8()?),
+            PTG_ATTR => return self.parse_attr(),
+
+            PTG_REF => {
+                let (row, col, row_relative, col_relative) = self.read_loc()?;
+                FormulaToken::CellRef {
+                    row,
+                    col,
+                    row_relative,
+                    col_relative,
+                }
+            },
+            PTG_REF_N => {
+                let (row, col, row_relative, col_relative) = self.read_loc()?;
+                let (row, col) = self.resolve(row, col, row_relative, col_relative);
+                FormulaToken::CellRef {
+                    row,
+                    col,
+                    row_relative,
+                    col_relative,
+                }
+            },
+            PTG_AREA => self.parse_area(None)?,
+            PTG_AREA_N => match self.parse_area(None)? {
+                FormulaToken::AreaRef {
+                    row_first,
+                    row_last,
+                    col_first,
+                    col_last,
+                    row_first_relative,
+                    row_last_relative,
+                    col_first_relative,
+                    col_last_relative,
+                } => {
+                    let (row_first, col_first) =
+                        self.resolve(row_first, col_first, row_first_relative, col_first_relative);
+                    let (row_last, col_last) =
+                        self.resolve(row_last, col_last, row_last_relative, col_last_relative);
+                    FormulaToken::AreaRef {
+                        row_first,
+                        row_last,
+                        col_first,
+                        col_last,
+                        row_first_relative,
+                        row_last_relative,
+                        col_first_relative,
+                        col_last_relative,
+                    }
+                },
+                token => token,
+            },
+            PTG_REF_3D => {
+                let ixti = self.read_u16()?;
+                let (row, col, row_relative, col_relative) = self.read_loc()?;
+                FormulaToken::CellRef3d {
+                    ixti,
+                    row,
+                    col,
+                    row_relative,
+                    col_relative,
+                }
+            },
+            PTG_AREA_3D => {
+                let ixti = self.read_u16()?;
+                self.parse_area(Some(ixti))?
+            },
+            PTG_REF_ERR => self.skip_ref_error(6)?,
+            PTG_AREA_ERR => self.skip_ref_error(12)?,
+            PTG_REF_ERR_3D => self.skip_ref_error(8)?,
+            PTG_AREA_ERR_3D => self.skip_ref_error(14)?,
+
+            // Headers of a reference subexpression that follows inline
+            PTG_MEM_AREA | PTG_MEM_ERR | PTG_MEM_NO_MEM => {
+                self.take(6)?;
+                return Ok(None);
+            },
+            PTG_MEM_FUNC => {
+                self.take(2)?;
+                return Ok(None);
             },
-        }
-    }
 
-    /// Parse integer constant
-    fn parse_int(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 2 > self.data.len() {
-            return Ok(None);
-        }
+            PTG_FUNC => {
+                let index = self.read_u16()?;
+                // PtgFunc is only used for functions with a fixed argument count
+                let arg_count = FunctionInfo::by_index(index).map_or(1, |f| f.min_args);
+                FormulaToken::Function { index, arg_count }
+            },
+            PTG_FUNC_VAR => {
+                let arg_count = self.read_u8()?;
+                // The high bit (fCeFunc) marks command-equivalent functions
+                let index = self.read_u16()? & 0x7FFF;
+                FormulaToken::Function { index, arg_count }
+            },
 
-        let value = binary::read_u16_le_at(self.data, self.offset)?;
-        self.offset += 2;
+            PTG_NAME => FormulaToken::Name(self.read_u32()?),
 
-        Ok(Some(FormulaToken::Int(value)))
-    }
+            _ => FormulaToken::Unknown(ptg_type),
+        };
 
-    /// Parse floating point constant
-    fn parse_num(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 8 > self.data.len() {
-            return Ok(None);
-        }
+        Ok(Some(token))
+    }
 
-        let value = binary::read_f64_le_at(self.data, self.offset)?;
-        self.offset += 8;
+    /// Parse string constant (`cch` u16 + UTF-16LE characters)
+    fn parse_str(&mut self) -> XlsbResult<FormulaToken> {
+        let len = self.read_u16()? as usize;
+        let bytes = self.take(len * 2)?;
+        let units: Vec<u16> = bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .collect();
 
-        Ok(Some(FormulaToken::Number(value)))
+        Ok(FormulaToken::String(String::from_utf16_lossy(&units)))
     }
 
-    /// Parse string constant
-    fn parse_str(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 1 > self.data.len() {
-            return Ok(None);
-        }
-
-        let len = self.data[self.offset] as usize;
-        self.offset += 1;
+    /// Parse a `PtgAttr`, which only produces a token for `SUM` with one argument
+    fn parse_attr(&mut self) -> XlsbResult<Option<FormulaToken>> {
+        use ptg_types::*;
 
-        if self.offset + len > self.data.len() {
-            return Ok(None);
+        let attr_type = self.read_u8()?;
+        let data = self.read_u16()?;
+
+        match attr_type {
+            ATTR_SUM => Ok(Some(FormulaToken::Function {
+                index: 4,
+                arg_count: 1,
+            })),
+            ATTR_CHOOSE => {
+                // Jump table with one offset per choice plus one
+                self.take((data as usize + 1) * 2)?;
+                Ok(None)
+            },
+            _ => Ok(None),
         }
-
-        // String is stored as UTF-8 (not UTF-16LE like in records)
-        let string =
-            String::from_utf8_lossy(&self.data[self.offset..self.offset + len]).into_owned();
-        self.offset += len;
-
-        Ok(Some(FormulaToken::String(string)))
     }
 
-    /// Parse boolean constant
-    fn parse_bool(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 1 > self.data.len() {
-            return Ok(None);
-        }
-
-        let value = self.data[self.offset] != 0;
-        self.offset += 1;
-
-        Ok(Some(FormulaToken::Bool(value)))
+    /// Parse an area (`RgceArea`), optionally on another sheet
+    fn parse_area(&mut self, ixti: Option<u16>) -> XlsbResult<FormulaToken> {
+        let row_first = self.read_u32()?;
+        let row_last = self.read_u32()?;
+        let (col_first, row_first_relative, col_first_relative) = Self::split_col(self.read_u16()?);
+        let (col_last, row_last_relative, col_last_relative) = Self::split_col(self.read_u16()?);
+
+        Ok(match ixti {
+            Some(ixti) => FormulaToken::AreaRef3d {
+                ixti,
+                row_first,
+                row_last,
+                col_first,
+                col_last,
+                row_first_relative,
+                row_last_relative,
+                col_first_relative,
+                col_last_relative,
+            },
+            None => FormulaToken::AreaRef {
+                row_first,
+                row_last,
+                col_first,
+                col_last,
+                row_first_relative,
+                row_last_relative,
+                col_first_relative,
+                col_last_relative,
+            },
+        })
     }
 
-    /// Parse error constant
-    fn parse_err(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 1 > self.data.len() {
-            return Ok(None);
-        }
-
-        let error_code = self.data[self.offset];
-        self.offset += 1;
-
-        Ok(Some(FormulaToken::Error(error_code)))
+    /// Skip the data of a reference that was deleted and return `#REF!`
+    fn skip_ref_error(&mut self, len: usize) -> XlsbResult<FormulaToken> {
+        self.take(len)?;
+        Ok(FormulaToken::Error(0x17))
     }
 
-    /// Parse cell reference
-    fn parse_ref(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 4 > self.data.len() {
-            return Ok(None);
-        }
-
-        let row_data = binary::read_u16_le_at(self.data, self.offset)?;
-        let col_data = binary::read_u16_le_at(self.data, self.offset + 2)?;
-        self.offset += 4;
-
-        // Extract row and column (with relative flags)
-        let row = (row_data & 0x3FFF) as u32;
-        let row_relative = (row_data & 0x8000) != 0;
-        let col = (col_data & 0x3FFF) as u32;
-        let col_relative = (col_data & 0x8000) != 0;
-
-        Ok(Some(FormulaToken::CellRef {
-            row,
-            col,
-            row_relative,
-            col_relative,
-        }))
+    /// Read a cell location (`RgceLoc`): row, column and relative flags
+    fn read_loc(&mut self) -> XlsbResult<(u32, u32, bool, bool)> {
+        let row = self.read_u32()?;
+        let (col, row_relative, col_relative) = Self::split_col(self.read_u16()?);
+        Ok((row, col, row_relative, col_relative))
     }
 
-    /// Parse area reference
-    fn parse_area(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 8 > self.data.len() {
-            return Ok(None);
-        }
-
-        let row_first_data = binary::read_u16_le_at(self.data, self.offset)?;
-        let row_last_data = binary::read_u16_le_at(self.data, self.offset + 2)?;
-        let col_first_data = binary::read_u16_le_at(self.data, self.offset + 4)?;
-        let col_last_data = binary::read_u16_le_at(self.data, self.offset + 6)?;
-        self.offset += 8;
-
-        let row_first = (row_first_data & 0x3FFF) as u32;
-        let row_first_relative = (row_first_data & 0x8000) != 0;
-        let row_last = (row_last_data & 0x3FFF) as u32;
-        let row_last_relative = (row_last_data & 0x8000) != 0;
-        let col_first = (col_first_data & 0x3FFF) as u32;
-        let col_first_relative = (col_first_data & 0x8000) != 0;
-        let col_last = (col_last_data & 0x3FFF) as u32;
-        let col_last_relative = (col_last_data & 0x8000) != 0;
-
-        Ok(Some(FormulaToken::AreaRef {
-            row_first,
-            row_last,
-            col_first,
-            col_last,
-            row_first_relative,
-            row_last_relative,
-            col_first_relative,
-            col_last_relative,
-        }))
+    /// Resolve the offsets of a `RgceLocRel` against the base cell
+    ///
+    /// The row offset is a signed 32-bit and the column offset a signed
+    /// 14-bit integer; absolute parts are kept as they are.
+    fn resolve(&self, row: u32, col: u32, row_relative: bool, col_relative: bool) -> (u32, u32) {
+        const ROWS: i64 = 1 << 20;
+        const COLS: i64 = 1 << 14;
+
+        let Some((base_row, base_col)) = self.base else {
+            return (row, col);
+        };
+        let row = if row_relative {
+            (i64::from(base_row) + i64::from(row as i32)).rem_euclid(ROWS) as u32
+        } else {
+            row
+        };
+        let col = if col_relative {
+            // Sign-extend the 14-bit offset
+            let offset = ((col << 18) as i32) >> 18;
+            (i64::from(base_col) + i64::from(offset)).rem_euclid(COLS) as u32
+        } else {
+            col
+        };
+        (row, col)
     }
 
-    /// Parse function with fixed arguments
-    fn parse_func(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 2 > self.data.len() {
-            return Ok(None);
-        }
-
-        let index = binary::read_u16_le_at(self.data, self.offset)?;
-        self.offset += 2;
-
-        // Look up argument count from function table (simplified)
-        let arg_count = Self::get_function_arg_count(index);
-
-        Ok(Some(FormulaToken::Function { index, arg_count }))
+    /// Split a `ColRelShort` into the column, fRwRel and fColRel
+    fn split_col(col_data: u16) -> (u32, bool, bool) {
+        (
+            (col_data & 0x3FFF) as u32,
+            col_data & 0x8000 != 0,
+            col_data & 0x4000 != 0,
+        )
     }
 
-    /// Parse function with variable arguments
-    fn parse_func_var(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 3 > self.data.len() {
-            return Ok(None);
-        }
-
-        let arg_count = self.data[self.offset];
-        let index = binary::read_u16_le_at(self.data, self.offset + 1)?;
-        self.offset += 3;
-
-        Ok(Some(FormulaToken::Function { index, arg_count }))
+    fn read_u8(&mut self) -> XlsbResult<u8> {
+        Ok(self.take(1)?[0])
     }
 
-    /// Parse defined name reference
-    fn parse_name(&mut self) -> XlsbResult<Option<FormulaToken>> {
-        if self.offset + 4 > self.data.len() {
-            return Ok(None);
-        }
-
-        let name_index = binary::read_u32_le_at(self.data, self.offset)?;
-        self.offset += 4;
+    fn read_u16(&mut self) -> XlsbResult<u16> {
+        binary::read_u16_le_at(self.take(2)?, 0).map_err(Into::into)
+    }
 
-        Ok(Some(FormulaToken::Name(name_index)))
+    fn read_u32(&mut self) -> XlsbResult<u32> {
+        binary::read_u32_le_at(self.take(4)?, 0).map_err(Into::into)
     }
 
-    /// Get function argument count by function index
-    ///
-    /// This is a simplified lookup. In a complete implementation, this would
-    /// use a comprehensive table of all Excel functions.
-    fn get_function_arg_count(index: u16) -> u8 {
-        match index {
-            0 => 1, // COUNT
-            4 => 2, // SUM (variable, but typically 1+)
-            1 => 2, // IF (typically 3, but can be 2)
-            _ => 1, // Default to 1 for unknown
+    /// Consume the next `len` bytes
+    fn take(&mut self, len: usize) -> XlsbResult<&'a [u8]> {
+        let end = self.offset + len;
+        if end > self.data.len() {
+            return Err(XlsbError::InvalidFormula(format!(
+                "token data ends at byte {} of {}",
+                end,
+                self.data.len()
+            )));
         }
+
+        let bytes = &self.data[self.offset..end];
+        self.offset = end;
+        Ok(bytes)
     }
 }
 
 /// Formula converter - converts tokens to human-readable formula string
 ///
-/// # Note
-///
-/// This is a simplified converter. A complete implementation would handle
-/// all token types and produce fully accurate Excel formula syntax.
+/// Parentheses only appear where the formula has a [`FormulaToken::Paren`],
+/// which is how Excel records the parentheses typed by the user.
 pub struct FormulaConverter;
 
 impl FormulaConverter {
     /// Convert formula tokens to string representation
     ///
-    /// Uses RPN to infix conversion with proper operator precedence.
+    /// References to other sheets become `#REF!`; use
+    /// [`tokens_to_string_with_sheets`](Self::tokens_to_string_with_sheets)
+    /// to resolve their sheet names.
     pub fn tokens_to_string(tokens: &[FormulaToken]) -> String {
+        Self::tokens_to_string_with_sheets(tokens, &[])
+    }
+
+    /// Convert formula tokens to string representation, resolving references
+    /// to other sheets through `sheets`.
+    ///
+    /// `sheets` holds the sheet name of each extern sheet table entry, indexed
+    /// by `ixti`. An empty name marks an entry that does not refer to a sheet
+    /// of this workbook.
+    pub fn tokens_to_string_with_sheets(tokens: &[FormulaToken], sheets: &[String]) -> String {
         let mut stack: Vec<String> = Vec::new();
 
         for token in tokens {
             match token {
                 FormulaToken::Number(n) => stack.push(format!("{}", n)),
                 FormulaToken::Int(i) => stack.push(format!("{}", i)),
-                FormulaToken::String(s) => stack.push(format!("\"{}\"", s)),
+                FormulaToken::String(s) => stack.push(format!("\"{}\"", s.replace('"', "\"\""))),
                 FormulaToken::Bool(b) => stack.push(if *b {
                     "TRUE".to_string()
                 } else {
@@ -454,53 +862,116 @@ impl FormulaConverter {
                     col,
                     row_relative,
                     col_relative,
-                } => {
-                    let col_str = crate::ooxml::xlsb::utils::column_index_to_name(*col + 1);
-                    let row_str = row + 1;
-                    let col_prefix = if *col_relative { "" } else { "$" };
-                    let row_prefix = if *row_relative { "" } else { "$" };
-                    stack.push(format!(
-                        "{}{}{}{}",
-                        col_prefix, col_str, row_prefix, row_str
-                    ));
-                },
+                } => stack.push(Self::cell_to_string(
+                    *row,
+                    *col,
+                    *row_relative,
+                    *col_relative,
+                )),
                 FormulaToken::AreaRef {
                     row_first,
+                    row_last,
                     col_first,
+                    col_last,
+                    row_first_relative,
+                    row_last_relative,
+                    col_first_relative,
+                    col_last_relative,
+                } => stack.push(format!(
+                    "{}:{}",
+                    Self::cell_to_string(
+                        *row_first,
+                        *col_first,
+                        *row_first_relative,
+                        *col_first_relative
+                    ),
+                    Self::cell_to_string(
+                        *row_last,
+                        *col_last,
+                        *row_last_relative,
+                        *col_last_relative
+                    )
+                )),
+                FormulaToken::CellRef3d {
+                    ixti,
+                    row,
+                    col,
+                    row_relative,
+                    col_relative,
+                } => stack.push(match Self::sheet_prefix(sheets, *ixti) {
+                    Some(prefix) => format!(
+                        "{}{}",
+                        prefix,
+                        Self::cell_to_string(*row, *col, *row_relative, *col_relative)
+                    ),
+                    None => "#REF!".to_string(),
+                }),
+                FormulaToken::AreaRef3d {
+                    ixti,
+                    row_first,
                     row_last,
+                    col_first,
                     col_last,
-                    ..
-                } => {
-                    let first = crate::ooxml::xlsb::utils::cell_reference(*row_first, *col_first);
-                    let last = crate::ooxml::xlsb::utils::cell_reference(*row_last, *col_last);
-                    stack.push(format!("{}:{}", first, last));
-                },
+                    row_first_relative,
+                    row_last_relative,
+                    col_first_relative,
+                    col_last_relative,
+                } => stack.push(match Self::sheet_prefix(sheets, *ixti) {
+                    Some(prefix) => format!(
+                        "{}{}:{}",
+                        prefix,
+                        Self::cell_to_string(
+                            *row_first,
+                            *col_first,
+                            *row_first_relative,
+                            *col_first_relative
+                        ),
+                        Self::cell_to_string(
+                            *row_last,
+                            *col_last,
+                            *row_last_relative,
+                            *col_last_relative
+                        )
+                    ),
+                    None => "#REF!".to_string(),
+                }),
                 FormulaToken::BinaryOp(op) => {
                     if stack.len() >= 2 {
                         let right = stack.pop().unwrap();
                         let left = stack.pop().unwrap();
                         let op_str = Self::binary_op_to_string(*op);
-                        stack.push(format!("({}{}{})", left, op_str, right));
+                        stack.push(format!("{}{}{}", left, op_str, right));
                     }
                 },
                 FormulaToken::UnaryOp(op) => {
-                    if !stack.is_empty() {
-                        let operand = stack.pop().unwrap();
+                    if let Some(operand) = stack.pop() {
                         match op {
-                            UnaryOperator::Plus => stack.push(format!("+({})", operand)),
-                            UnaryOperator::Minus => stack.push(format!("-({})", operand)),
-                            UnaryOperator::Percent => stack.push(format!("({}%)", operand)),
+                            UnaryOperator::Plus => stack.push(format!("+{}", operand)),
+                            UnaryOperator::Minus => stack.push(format!("-{}", operand)),
+                            UnaryOperator::Percent => stack.push(format!("{}%", operand)),
                         }
                     }
                 },
+                FormulaToken::Paren => {
+                    if let Some(operand) = stack.pop() {
+                        stack.push(format!("({})", operand));
+                    }
+                },
+                FormulaToken::MissingArg => stack.push(String::new()),
                 FormulaToken::Function { index, arg_count } => {
-                    let func_name = Self::function_name(*index);
                     let mut args = Vec::new();
                     for _ in 0..*arg_count {
                         if let Some(arg) = stack.pop() {
                             args.insert(0, arg);
                         }
                     }
+                    // Add-in and newer functions (index 255) take their name
+                    // as the first argument
+                    let func_name = if *index == 255 && !args.is_empty() {
+                        args.remove(0)
+                    } else {
+                        Self::function_name(*index)
+                    };
                     stack.push(format!("{}({})", func_name, args.join(",")));
                 },
                 FormulaToken::Name(idx) => stack.push(format!("Name{}", idx)),
@@ -511,6 +982,23 @@ impl FormulaConverter {
         stack.pop().unwrap_or_default()
     }
 
+    /// Format a cell reference such as `B$3`
+    fn cell_to_string(row: u32, col: u32, row_relative: bool, col_relative: bool) -> String {
+        format!(
+            "{}{}{}{}",
+            if col_relative { "" } else { "$" },
+            crate::ooxml::xlsb::utils::column_index_to_name(col + 1),
+            if row_relative { "" } else { "$" },
+            row + 1
+        )
+    }
+
+    /// The `Sheet!` prefix of a 3D reference, or `None` if it does not resolve
+    fn sheet_prefix(sheets: &[String], ixti: u16) -> Option<String> {
+        let name = sheets.get(ixti as usize).filter(|name| !name.is_empty())?;
+        Some(format!("{}!", quote_sheet_name(name)))
+    }
+
     /// Convert binary operator to string
     fn binary_op_to_string(op: BinaryOperator) -> &'static str {
         match op {
@@ -526,38 +1014,803 @@ impl FormulaConverter {
             BinaryOperator::GreaterEqual => ">=",
             BinaryOperator::GreaterThan => ">",
             BinaryOperator::NotEqual => "<>",
+            BinaryOperator::Intersect => " ",
+            BinaryOperator::Union => ",",
+            BinaryOperator::Range => ":",
         }
     }
 
     /// Convert error code to string
     fn error_to_string(code: u8) -> String {
-        match code {
-            0x00 => "#NULL!".to_string(),
-            0x07 => "#DIV/0!".to_string(),
-            0x0F => "#VALUE!".to_string(),
-            0x17 => "#REF!".to_string(),
-            0x1D => "#NAME?".to_string(),
-            0x24 => "#NUM!".to_string(),
-            0x2A => "#N/A".to_string(),
-            0x2B => "#GETTING_DATA".to_string(),
-            _ => format!("#ERR{:02X}!", code),
-        }
+        ERROR_CODES.iter().find(|(c, _)| *c == code).map_or_else(
+            || format!("#ERR{:02X}!", code),
+            |(_, text)| text.to_string(),
+        )
     }
 
-    /// Get function name by index (simplified)
+    /// Get function name by index
     fn function_name(index: u16) -> String {
-        match index {
-            0 => "COUNT".to_string(),
-            1 => "IF".to_string(),
-            4 => "SUM".to_string(),
-            5 => "AVERAGE".to_string(),
-            6 => "MIN".to_string(),
-            7 => "MAX".to_string(),
-            _ => format!("FUNC{}", index),
+        match FunctionInfo::by_index(index) {
+            Some(info) => info.name.to_string(),
+            None => format!("FUNC{}", index),
         }
     }
 }
 
+/// Quote a sheet name for use in a reference when it is not a plain identifier.
+fn quote_sheet_name(name: &str) -> String {
+    let plain = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+        && parse_cell(name).is_none();
+
+    if plain {
+        name.to_string()
+    } else {
+        format!("'{}'", name.replace('\'', "''"))
+    }
+}
+
+/// Parse an A1-style cell reference into (row, col, row_relative, col_relative).
+fn parse_cell(text: &str) -> Option<(u32, u32, bool, bool)> {
+    let (col_relative, rest) = match text.strip_prefix('$') {
+        Some(rest) => (false, rest),
+        None => (true, text),
+    };
+    let letters = rest.chars().take_while(char::is_ascii_alphabetic).count();
+    if letters == 0 || letters > 3 {
+        return None;
+    }
+    let (col_name, rest) = rest.split_at(letters);
+    let (row_relative, digits) = match rest.strip_prefix('$') {
+        Some(digits) => (false, digits),
+        None => (true, rest),
+    };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let col = crate::ooxml::xlsb::utils::column_name_to_index(col_name)?;
+    let row = digits.parse::<u32>().ok()?.checked_sub(1)?;
+    if row > MAX_ROW || col > MAX_COL {
+        return None;
+    }
+
+    Some((row, col, row_relative, col_relative))
+}
+
+/// Formula encoder - compiles formula text into XLSB formula bytes (`rgce`)
+///
+/// Supports numbers, strings, booleans, error constants, cell and area
+/// references (with `$` absolute markers and an optional `Sheet!` or
+/// `'Sheet Name'!` prefix), the arithmetic, comparison, `&`, `%` and `:`
+/// operators, parentheses, and calls to built-in functions. Defined names,
+/// array constants and the intersection and union operators are not
+/// supported.
+///
+/// # Example
+///
+/// ```rust
+/// use litchi::ooxml::xlsb::formula::{FormulaConverter, FormulaEncoder, FormulaParser};
+///
+/// let sheets = vec!["Data".to_string()];
+/// let rgce = FormulaEncoder::new(&sheets).encode("A1*2+SUM(Data!$B$1:$B$10)").unwrap();
+///
+/// // Files written by `XlsbWorkbookWriter` put the first sheet at extern sheet index 2
+/// let extern_sheets = vec![String::new(), String::new(), "Data".to_string()];
+/// let tokens = FormulaParser::new(&rgce).parse().unwrap();
+/// assert_eq!(
+///     FormulaConverter::tokens_to_string_with_sheets(&tokens, &extern_sheets),
+///     "A1*2+SUM(Data!$B$1:$B$10)"
+/// );
+/// ```
+pub struct FormulaEncoder<'a> {
+    sheet_names: Option<&'a [String]>,
+}
+
+impl<'a> FormulaEncoder<'a> {
+    /// Create an encoder for a workbook with the given worksheets, in order.
+    ///
+    /// Sheet references use the extern sheet table written by
+    /// [`XlsbWorkbookWriter`](crate::ooxml::xlsb::writer::XlsbWorkbookWriter).
+    pub fn new(sheet_names: &'a [String]) -> Self {
+        FormulaEncoder {
+            sheet_names: Some(sheet_names),
+        }
+    }
+
+    /// Create an encoder that only checks syntax, accepting any sheet name.
+    pub(crate) fn syntax_only() -> Self {
+        FormulaEncoder { sheet_names: None }
+    }
+
+    /// Encode a formula, with or without a leading `=`, into `rgce` bytes.
+    pub fn encode(&self, formula: &str) -> XlsbResult<Vec<u8>> {
+        let formula = formula.strip_prefix('=').unwrap_or(formula);
+        let mut parser = ExprParser {
+            encoder: self,
+            lexemes: lex(formula)?,
+            pos: 0,
+        };
+        let node = parser.parse_formula()?;
+
+        let mut rgce = Vec::new();
+        write_node(&node, ptg_types::PTG_CLASS_VALUE, &mut rgce);
+        Ok(rgce)
+    }
+
+    /// Resolve a sheet name to its extern sheet index.
+    fn sheet_ixti(&self, name: &str) -> XlsbResult<u16> {
+        let Some(sheet_names) = self.sheet_names else {
+            return Ok(FIRST_SHEET_IXTI);
+        };
+
+        sheet_names
+            .iter()
+            .position(|sheet| sheet.eq_ignore_ascii_case(name))
+            .map(|index| index as u16 + FIRST_SHEET_IXTI)
+            .ok_or_else(|| XlsbError::WorksheetNotFound(name.to_string()))
+    }
+}
+
+/// A lexical element of formula text
+#[derive(Debug, Clone, PartialEq)]
+enum Lexeme {
+    Number(f64),
+    Text(String),
+    Error(u8),
+    /// Function name, cell reference, boolean or defined name
+    Word(String),
+    /// `Sheet!` or `'Sheet Name'!`
+    Sheet(String),
+    Operator(&'static str),
+    Percent,
+    LeftParen,
+    RightParen,
+    Comma,
+    Colon,
+}
+
+/// Split formula text into lexemes.
+fn lex(formula: &str) -> XlsbResult<Vec<Lexeme>> {
+    let chars: Vec<char> = formula.chars().collect();
+    let mut lexemes = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            ' ' | '\t' | '\r' | '\n' => i += 1,
+            '"' | '\'' => {
+                // Quotes are escaped by doubling them
+                let mut text = String::new();
+                i += 1;
+                loop {
+                    match chars.get(i) {
+                        Some(&q) if q == c && chars.get(i + 1) == Some(&c) => {
+                            text.push(c);
+                            i += 2;
+                        },
+                        Some(&q) if q == c => {
+                            i += 1;
+                            break;
+                        },
+                        Some(&other) => {
+                            text.push(other);
+                            i += 1;
+                        },
+                        None => {
+                            return Err(XlsbError::InvalidFormula(format!(
+                                "unterminated {} in {}",
+                                if c == '"' { "string" } else { "sheet name" },
+                                formula
+                            )));
+                        },
+                    }
+                }
+
+                if c == '"' {
+                    lexemes.push(Lexeme::Text(text));
+                } else if chars.get(i) == Some(&'!') {
+                    lexemes.push(Lexeme::Sheet(text));
+                    i += 1;
+                } else {
+                    return Err(XlsbError::InvalidFormula(format!(
+                        "expected '!' after sheet name '{}'",
+                        text
+                    )));
+                }
+            },
+            '#' => {
+                let rest: String = chars[i..].iter().collect::<String>().to_ascii_uppercase();
+                let (code, text) = ERROR_CODES
+                    .iter()
+                    .find(|(_, text)| rest.starts_with(text))
+                    .ok_or_else(|| {
+                        XlsbError::InvalidFormula(format!("unknown error constant in {}", formula))
+                    })?;
+                lexemes.push(Lexeme::Error(*code));
+                i += text.chars().count();
+            },
+            '0'..='9' | '.' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                    i += 1;
+                }
+                if i < chars.len() && matches!(chars[i], 'e' | 'E') {
+                    let mut j = i + 1;
+                    if j < chars.len() && matches!(chars[j], '+' | '-') {
+                        j += 1;
+                    }
+                    if j < chars.len() && chars[j].is_ascii_digit() {
+                        i = j;
+                        while i < chars.len() && chars[i].is_ascii_digit() {
+                            i += 1;
+                        }
+                    }
+                }
+                let text: String = chars[start..i].iter().collect();
+                let number = text
+                    .parse::<f64>()
+                    .map_err(|_| XlsbError::InvalidFormula(format!("invalid number {}", text)))?;
+                lexemes.push(Lexeme::Number(number));
+            },
+            c if c.is_alphabetic() || c == '_' || c == '$' || c == '\\' => {
+                let start = i;
+                while i < chars.len()
+                    && (chars[i].is_alphanumeric() || matches!(chars[i], '_' | '.' | '$' | '\\'))
+                {
+                    i += 1;
+                }
+                let word: String = chars[start..i].iter().collect();
+                if chars.get(i) == Some(&'!') {
+                    lexemes.push(Lexeme::Sheet(word));
+                    i += 1;
+                } else {
+                    lexemes.push(Lexeme::Word(word));
+                }
+            },
+            '(' => {
+                lexemes.push(Lexeme::LeftParen);
+                i += 1;
+            },
+            ')' => {
+                lexemes.push(Lexeme::RightParen);
+                i += 1;
+            },
+            ',' => {
+                lexemes.push(Lexeme::Comma);
+                i += 1;
+            },
+            ':' => {
+                lexemes.push(Lexeme::Colon);
+                i += 1;
+            },
+            '%' => {
+                lexemes.push(Lexeme::Percent);
+                i += 1;
+            },
+            '<' | '>' => {
+                let op = match (c, chars.get(i + 1)) {
+                    ('<', Some('=')) => "<=",
+                    ('<', Some('>')) => "<>",
+                    ('>', Some('=')) => ">=",
+                    ('<', _) => "<",
+                    _ => ">",
+                };
+                lexemes.push(Lexeme::Operator(op));
+                i += op.len();
+            },
+            '+' | '-' | '*' | '/' | '^' | '&' | '=' => {
+                let op = match c {
+                    '+' => "+",
+                    '-' => "-",
+                    '*' => "*",
+                    '/' => "/",
+                    '^' => "^",
+                    '&' => "&",
+                    _ => "=",
+                };
+                lexemes.push(Lexeme::Operator(op));
+                i += 1;
+            },
+            other => {
+                return Err(XlsbError::InvalidFormula(format!(
+                    "unexpected character '{}' in {}",
+                    other, formula
+                )));
+            },
+        }
+    }
+
+    Ok(lexemes)
+}
+
+/// Parsed formula expression
+enum Node {
+    Operand(FormulaToken),
+    Unary(UnaryOperator, Box<Node>),
+    Binary(BinaryOperator, Box<Node>, Box<Node>),
+    Paren(Box<Node>),
+    Function(&'static FunctionInfo, Vec<Node>),
+}
+
+/// Recursive descent parser over lexemes, following Excel's operator
+/// precedence: `:`, then unary `-`/`+`, `%`, `^`, `*`/`/`, `+`/`-`, `&` and
+/// finally the comparisons.
+struct ExprParser<'e, 'a> {
+    encoder: &'e FormulaEncoder<'a>,
+    lexemes: Vec<Lexeme>,
+    pos: usize,
+}
+
+impl ExprParser<'_, '_> {
+    fn parse_formula(&mut self) -> XlsbResult<Node> {
+        let node = self.comparison()?;
+        match self.peek() {
+            None => Ok(node),
+            Some(lexeme) => Err(XlsbError::InvalidFormula(format!(
+                "unexpected {:?}",
+                lexeme
+            ))),
+        }
+    }
+
+    fn comparison(&mut self) -> XlsbResult<Node> {
+        let mut left = self.concat()?;
+        while let Some(op) = self.eat_operator(&["=", "<>", "<", "<=", ">", ">="]) {
+            let right = self.concat()?;
+            let op = match op {
+                "=" => BinaryOperator::Equal,
+                "<>" => BinaryOperator::NotEqual,
+                "<" => BinaryOperator::LessThan,
+                "<=" => BinaryOperator::LessEqual,
+                ">" => BinaryOperator::GreaterThan,
+                _ => BinaryOperator::GreaterEqual,
+            };
+            left = Node::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn concat(&mut self) -> XlsbResult<Node> {
+        let mut left = self.additive()?;
+        while self.eat_operator(&["&"]).is_some() {
+            let right = self.additive()?;
+            left = Node::Binary(BinaryOperator::Concat, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn additive(&mut self) -> XlsbResult<Node> {
+        let mut left = self.multiplicative()?;
+        while let Some(op) = self.eat_operator(&["+", "-"]) {
+            let right = self.multiplicative()?;
+            let op = if op == "+" {
+                BinaryOperator::Add
+            } else {
+                BinaryOperator::Subtract
+            };
+            left = Node::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn multiplicative(&mut self) -> XlsbResult<Node> {
+        let mut left = self.power()?;
+        while let Some(op) = self.eat_operator(&["*", "/"]) {
+            let right = self.power()?;
+            let op = if op == "*" {
+                BinaryOperator::Multiply
+            } else {
+                BinaryOperator::Divide
+            };
+            left = Node::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn power(&mut self) -> XlsbResult<Node> {
+        let mut left = self.percent()?;
+        while self.eat_operator(&["^"]).is_some() {
+            let right = self.percent()?;
+            left = Node::Binary(BinaryOperator::Power, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn percent(&mut self) -> XlsbResult<Node> {
+        let mut node = self.unary()?;
+        while self.peek() == Some(&Lexeme::Percent) {
+            self.pos += 1;
+            node = Node::Unary(UnaryOperator::Percent, Box::new(node));
+        }
+        Ok(node)
+    }
+
+    fn unary(&mut self) -> XlsbResult<Node> {
+        match self.eat_operator(&["-", "+"]) {
+            Some("-") => Ok(Node::Unary(UnaryOperator::Minus, Box::new(self.unary()?))),
+            Some(_) => Ok(Node::Unary(UnaryOperator::Plus, Box::new(self.unary()?))),
+            None => self.range(),
+        }
+    }
+
+    fn range(&mut self) -> XlsbResult<Node> {
+        let mut left = self.primary()?;
+        while self.peek() == Some(&Lexeme::Colon) {
+            self.pos += 1;
+            let right = self.primary()?;
+            left = Node::Binary(BinaryOperator::Range, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn primary(&mut self) -> XlsbResult<Node> {
+        let lexeme = self
+            .next()
+            .ok_or_else(|| XlsbError::InvalidFormula("unexpected end of formula".to_string()))?;
+
+        match lexeme {
+            Lexeme::Number(n) => Ok(Node::Operand(
+                if n.fract() == 0.0 && (0.0..=u16::MAX as f64).contains(&n) {
+                    FormulaToken::Int(n as u16)
+                } else {
+                    FormulaToken::Number(n)
+                },
+            )),
+            Lexeme::Text(text) => {
+                if text.encode_utf16().count() > MAX_STRING_LEN {
+                    return Err(XlsbError::InvalidFormula(format!(
+                        "string constants are limited to {} characters",
+                        MAX_STRING_LEN
+                    )));
+                }
+                Ok(Node::Operand(FormulaToken::String(text)))
+            },
+            Lexeme::Error(code) => Ok(Node::Operand(FormulaToken::Error(code))),
+            Lexeme::LeftParen => {
+                let inner = self.comparison()?;
+                self.expect(Lexeme::RightParen)?;
+                Ok(Node::Paren(Box::new(inner)))
+            },
+            Lexeme::Sheet(sheet) => {
+                let ixti = self.encoder.sheet_ixti(&sheet)?;
+                match self.next() {
+                    Some(Lexeme::Word(word)) => self.reference(&word, Some(ixti)),
+                    other => Err(XlsbError::InvalidFormula(format!(
+                        "expected a cell reference after {}!, found {:?}",
+                        sheet, other
+                    ))),
+                }
+            },
+            Lexeme::Word(word) => {
+                if self.peek() == Some(&Lexeme::LeftParen) {
+                    self.pos += 1;
+                    return self.function(&word);
+                }
+                if word.eq_ignore_ascii_case("TRUE") {
+                    return Ok(Node::Operand(FormulaToken::Bool(true)));
+                }
+                if word.eq_ignore_ascii_case("FALSE") {
+                    return Ok(Node::Operand(FormulaToken::Bool(false)));
+                }
+                self.reference(&word, None)
+            },
+            other => Err(XlsbError::InvalidFormula(format!("unexpected {:?}", other))),
+        }
+    }
+
+    /// Parse a cell reference, or an area if `first` is followed by `:` and a
+    /// second cell reference.
+    fn reference(&mut self, first: &str, ixti: Option<u16>) -> XlsbResult<Node> {
+        let (row, col, row_relative, col_relative) = parse_cell(first).ok_or_else(|| {
+            XlsbError::UnsupportedFeature(format!("name or reference '{}' in formula", first))
+        })?;
+
+        let last = match (self.lexemes.get(self.pos), self.lexemes.get(self.pos + 1)) {
+            (Some(Lexeme::Colon), Some(Lexeme::Word(word)))
+                if self.lexemes.get(self.pos + 2) != Some(&Lexeme::LeftParen) =>
+            {
+                parse_cell(word)
+            },
+            _ => None,
+        };
+
+        let Some((mut row_last, mut col_last, mut row_last_relative, mut col_last_relative)) = last
+        else {
+            return Ok(Node::Operand(match ixti {
+                Some(ixti) => FormulaToken::CellRef3d {
+                    ixti,
+                    row,
+                    col,
+                    row_relative,
+                    col_relative,
+                },
+                None => FormulaToken::CellRef {
+                    row,
+                    col,
+                    row_relative,
+                    col_relative,
+                },
+            }));
+        };
+        self.pos += 2;
+
+        // Areas are stored top-left to bottom-right
+        let (mut row_first, mut col_first) = (row, col);
+        let (mut row_first_relative, mut col_first_relative) = (row_relative, col_relative);
+        if row_first > row_last {
+            std::mem::swap(&mut row_first, &mut row_last);
+            std::mem::swap(&mut row_first_relative, &mut row_last_relative);
+        }
+        if col_first > col_last {
+            std::mem::swap(&mut col_first, &mut col_last);
+            std::mem::swap(&mut col_first_relative, &mut col_last_relative);
+        }
+
+        Ok(Node::Operand(match ixti {
+            Some(ixti) => FormulaToken::AreaRef3d {
+                ixti,
+                row_first,
+                row_last,
+                col_first,
+                col_last,
+                row_first_relative,
+                row_last_relative,
+                col_first_relative,
+                col_last_relative,
+            },
+            None => FormulaToken::AreaRef {
+                row_first,
+                row_last,
+                col_first,
+                col_last,
+                row_first_relative,
+                row_last_relative,
+                col_first_relative,
+                col_last_relative,
+            },
+        }))
+    }
+
+    /// Parse the arguments of a function call, after its opening parenthesis.
+    fn function(&mut self, name: &str) -> XlsbResult<Node> {
+        let info = FunctionInfo::by_name(name)
+            .ok_or_else(|| XlsbError::UnsupportedFeature(format!("function {}", name)))?;
+
+        let mut args = Vec::new();
+        if self.peek() == Some(&Lexeme::RightParen) {
+            self.pos += 1;
+        } else {
+            loop {
+                if matches!(self.peek(), Some(Lexeme::Comma | Lexeme::RightParen)) {
+                    args.push(Node::Operand(FormulaToken::MissingArg));
+                } else {
+                    args.push(self.comparison()?);
+                }
+
+                match self.next() {
+                    Some(Lexeme::Comma) => {},
+                    Some(Lexeme::RightParen) => break,
+                    other => {
+                        return Err(XlsbError::InvalidFormula(format!(
+                            "expected ',' or ')' in {}(), found {:?}",
+                            info.name, other
+                        )));
+                    },
+                }
+            }
+        }
+
+        if args.len() < info.min_args as usize || args.len() > info.max_args as usize {
+            return Err(XlsbError::InvalidFormula(format!(
+                "{} takes {} to {} arguments, got {}",
+                info.name,
+                info.min_args,
+                info.max_args,
+                args.len()
+            )));
+        }
+
+        Ok(Node::Function(info, args))
+    }
+
+    fn peek(&self) -> Option<&Lexeme> {
+        self.lexemes.get(self.pos)
+    }
+
+    fn next(&mut self) -> Option<Lexeme> {
+        let lexeme = self.lexemes.get(self.pos).cloned();
+        self.pos += 1;
+        lexeme
+    }
+
+    fn expect(&mut self, expected: Lexeme) -> XlsbResult<()> {
+        match self.next() {
+            Some(lexeme) if lexeme == expected => Ok(()),
+            other => Err(XlsbError::InvalidFormula(format!(
+                "expected {:?}, found {:?}",
+                expected, other
+            ))),
+        }
+    }
+
+    /// Consume the next lexeme if it is one of `ops`.
+    fn eat_operator(&mut self, ops: &[&'static str]) -> Option<&'static str> {
+        match self.peek() {
+            Some(Lexeme::Operator(op)) if ops.contains(op) => {
+                let op = *op;
+                self.pos += 1;
+                Some(op)
+            },
+            _ => None,
+        }
+    }
+}
+
+/// Write a parsed expression in RPN order.
+///
+/// `class` is the Ptg class (reference or value) that operands and function
+/// calls at this position are written with. Function arguments and the
+/// operands of `:` are references; everything else is a value.
+fn write_node(node: &Node, class: u8, rgce: &mut Vec<u8>) {
+    use ptg_types::*;
+
+    match node {
+        Node::Operand(token) => write_token(token, class, rgce),
+        Node::Unary(op, operand) => {
+            write_node(operand, PTG_CLASS_VALUE, rgce);
+            rgce.push(op.ptg());
+        },
+        Node::Binary(op, left, right) => {
+            let operand_class = if op.is_reference_operator() {
+                PTG_CLASS_REFERENCE
+            } else {
+                PTG_CLASS_VALUE
+            };
+            write_node(left, operand_class, rgce);
+            write_node(right, operand_class, rgce);
+            rgce.push(op.ptg());
+        },
+        Node::Paren(inner) => {
+            write_node(inner, class, rgce);
+            rgce.push(PTG_PAREN);
+        },
+        Node::Function(info, args) => {
+            for arg in args {
+                write_node(arg, PTG_CLASS_REFERENCE, rgce);
+            }
+            let token = FormulaToken::Function {
+                index: info.index,
+                arg_count: args.len() as u8,
+            };
+            write_token(&token, PTG_CLASS_VALUE, rgce);
+        },
+    }
+}
+
+/// Write a single token; `class` applies to references, names and functions.
+fn write_token(token: &FormulaToken, class: u8, rgce: &mut Vec<u8>) {
+    use ptg_types::*;
+
+    match token {
+        FormulaToken::Number(n) => {
+            rgce.push(PTG_NUM);
+            rgce.extend_from_slice(&n.to_le_bytes());
+        },
+        FormulaToken::Int(i) => {
+            rgce.push(PTG_INT);
+            rgce.extend_from_slice(&i.to_le_bytes());
+        },
+        FormulaToken::String(s) => {
+            let units: Vec<u16> = s.encode_utf16().collect();
+            rgce.push(PTG_STR);
+            rgce.extend_from_slice(&(units.len() as u16).to_le_bytes());
+            for unit in units {
+                rgce.extend_from_slice(&unit.to_le_bytes());
+            }
+        },
+        FormulaToken::Bool(b) => rgce.extend_from_slice(&[PTG_BOOL, *b as u8]),
+        FormulaToken::Error(code) => rgce.extend_from_slice(&[PTG_ERR, *code]),
+        FormulaToken::CellRef {
+            row,
+            col,
+            row_relative,
+            col_relative,
+        } => {
+            rgce.push(PTG_REF + class);
+            write_loc(rgce, *row, *col, *row_relative, *col_relative);
+        },
+        FormulaToken::AreaRef {
+            row_first,
+            row_last,
+            col_first,
+            col_last,
+            row_first_relative,
+            row_last_relative,
+            col_first_relative,
+            col_last_relative,
+        } => {
+            rgce.push(PTG_AREA + class);
+            rgce.extend_from_slice(&row_first.to_le_bytes());
+            rgce.extend_from_slice(&row_last.to_le_bytes());
+            write_col(rgce, *col_first, *row_first_relative, *col_first_relative);
+            write_col(rgce, *col_last, *row_last_relative, *col_last_relative);
+        },
+        FormulaToken::CellRef3d {
+            ixti,
+            row,
+            col,
+            row_relative,
+            col_relative,
+        } => {
+            rgce.push(PTG_REF_3D + class);
+            rgce.extend_from_slice(&ixti.to_le_bytes());
+            write_loc(rgce, *row, *col, *row_relative, *col_relative);
+        },
+        FormulaToken::AreaRef3d {
+            ixti,
+            row_first,
+            row_last,
+            col_first,
+            col_last,
+            row_first_relative,
+            row_last_relative,
+            col_first_relative,
+            col_last_relative,
+        } => {
+            rgce.push(PTG_AREA_3D + class);
+            rgce.extend_from_slice(&ixti.to_le_bytes());
+            rgce.extend_from_slice(&row_first.to_le_bytes());
+            rgce.extend_from_slice(&row_last.to_le_bytes());
+            write_col(rgce, *col_first, *row_first_relative, *col_first_relative);
+            write_col(rgce, *col_last, *row_last_relative, *col_last_relative);
+        },
+        FormulaToken::BinaryOp(op) => rgce.push(op.ptg()),
+        FormulaToken::UnaryOp(op) => rgce.push(op.ptg()),
+        FormulaToken::Paren => rgce.push(PTG_PAREN),
+        FormulaToken::MissingArg => rgce.push(PTG_MISSING_ARG),
+        FormulaToken::Function { index, arg_count } => {
+            if FunctionInfo::by_index(*index).is_some_and(FunctionInfo::has_fixed_args) {
+                rgce.push(PTG_FUNC + class);
+                rgce.extend_from_slice(&index.to_le_bytes());
+            } else {
+                rgce.push(PTG_FUNC_VAR + class);
+                rgce.push(*arg_count);
+                rgce.extend_from_slice(&index.to_le_bytes());
+            }
+        },
+        FormulaToken::Name(index) => {
+            rgce.push(PTG_NAME + class);
+            rgce.extend_from_slice(&index.to_le_bytes());
+        },
+        FormulaToken::Unknown(ptg) => rgce.push(*ptg),
+    }
+}
+
+/// Write a cell location (`RgceLoc`).
+fn write_loc(rgce: &mut Vec<u8>, row: u32, col: u32, row_relative: bool, col_relative: bool) {
+    rgce.extend_from_slice(&row.to_le_bytes());
+    write_col(rgce, col, row_relative, col_relative);
+}
+
+/// Write a `ColRelShort`: the column in the low 14 bits, then fColRel and fRwRel.
+fn write_col(rgce: &mut Vec<u8>, col: u32, row_relative: bool, col_relative: bool) {
+    let mut value = (col as u16) & 0x3FFF;
+    if col_relative {
+        value |= 0x4000;
+    }
+    if row_relative {
+        value |= 0x8000;
+    }
+    rgce.extend_from_slice(&value.to_le_bytes());
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -595,6 +1848,133 @@ mod tests {
             FormulaToken::BinaryOp(BinaryOperator::Add),
         ];
         let formula = FormulaConverter::tokens_to_string(&tokens);
-        assert_eq!(formula, "(1+2)");
+        assert_eq!(formula, "1+2");
+
+        let mut tokens = tokens;
+        tokens.push(FormulaToken::Paren);
+        tokens.push(FormulaToken::Int(3));
+        tokens.push(FormulaToken::BinaryOp(BinaryOperator::Multiply));
+        assert_eq!(FormulaConverter::tokens_to_string(&tokens), "(1+2)*3");
+    }
+
+    fn round_trip(formula: &str, sheet_names: &[String]) -> String {
+        let rgce = FormulaEncoder::new(sheet_names).encode(formula).unwrap();
+        let tokens = FormulaParser::new(&rgce).parse().unwrap();
+        let mut extern_sheets = vec![String::new(); FIRST_SHEET_IXTI as usize];
+        extern_sheets.extend_from_slice(sheet_names);
+        FormulaConverter::tokens_to_string_with_sheets(&tokens, &extern_sheets)
+    }
+
+    #[test]
+    fn test_encode_round_trip() {
+        let sheets = vec![
+            "Data".to_string(),
+            "My Sheet".to_string(),
+            "It's".to_string(),
+        ];
+        for formula in [
+            "A1*2+SUM(B1:B10)",
+            "$A$1+A$1+$A1",
+            "SUM($B$2:C$9)",
+            "Data!A1+'My Sheet'!$B$2:$C$3",
+            "SUM('It''s'!A1:A3)",
+            "(1+2)*3^2-4/5%",
+            "-A1&\"x\"\"y\"",
+            "IF(A1>=10,\"big\",IF(A1<>0,TRUE,#N/A))",
+            "ROUND(A1,2)",
+            "IF(A1,,B1)",
+            "A1:B2:C3",
+        ] {
+            assert_eq!(round_trip(formula, &sheets), formula);
+        }
+
+        // Numbers beyond PtgInt are stored as doubles
+        assert_eq!(round_trip("1.5E+3+0.25+70000", &sheets), "1500+0.25+70000");
+        // Areas are normalized, names are case-insensitive
+        assert_eq!(round_trip("=sum(data!B3:A1)", &sheets), "SUM(Data!A1:B3)");
+    }
+
+    #[test]
+    fn test_encode_token_layout() {
+        let rgce = FormulaEncoder::new(&[]).encode("B$3").unwrap();
+        // PtgRef (value class) + row u32 + column with fColRel set, fRwRel clear
+        assert_eq!(rgce, [0x44, 2, 0, 0, 0, 0x01, 0x40]);
+
+        let sheets = vec!["S".to_string()];
+        let rgce = FormulaEncoder::new(&sheets).encode("SUM(S!A1:A2)").unwrap();
+        assert_eq!(rgce[0], ptg_types::PTG_AREA_3D); // reference class inside functions
+        assert_eq!(&rgce[1..3], &2u16.to_le_bytes());
+        assert_eq!(&rgce[rgce.len() - 4..], &[0x42, 1, 4, 0]); // PtgFuncVar SUM, 1 arg
+
+        let rgce = FormulaEncoder::new(&[]).encode("ABS(1)").unwrap();
+        assert_eq!(rgce, [0x1E, 1, 0, 0x41, 24, 0]); // PtgFunc ABS
+
+        let rgce = FormulaEncoder::new(&[]).encode("\"é\"").unwrap();
+        assert_eq!(rgce, [0x17, 1, 0, 0xE9, 0]);
+    }
+
+    #[test]
+    fn test_encode_errors() {
+        let sheets = vec!["Data".to_string()];
+        let encoder = FormulaEncoder::new(&sheets);
+        assert!(matches!(
+            encoder.encode("Other!A1"),
+            Err(XlsbError::WorksheetNotFound(name)) if name == "Other"
+        ));
+        assert!(matches!(
+            encoder.encode("NOSUCHFUNC(1)"),
+            Err(XlsbError::UnsupportedFeature(_))
+        ));
+        assert!(matches!(
+            encoder.encode("MyName+1"),
+            Err(XlsbError::UnsupportedFeature(_))
+        ));
+        for invalid in ["ROUND(1)", "A1+", "(A1", "\"open", "A1 ; B1"] {
+            assert!(
+                matches!(encoder.encode(invalid), Err(XlsbError::InvalidFormula(_))),
+                "{invalid}"
+            );
+        }
+
+        // Sheet names are only checked once the workbook is known
+        assert!(FormulaEncoder::syntax_only().encode("Other!A1").is_ok());
+    }
+
+    #[test]
+    fn test_parse_skips_attr_tokens() {
+        // PtgAttrSpace, PtgRef A1, PtgAttrSum
+        let data = [
+            0x19, 0x40, 0x00, 0x01, 0x24, 0, 0, 0, 0, 0, 0xC0, 0x19, 0x10, 0, 0,
+        ];
+        let tokens = FormulaParser::new(&data).parse().unwrap();
+        assert_eq!(FormulaConverter::tokens_to_string(&tokens), "SUM(A1)");
+
+        // Truncated tokens are reported rather than misread
+        assert!(FormulaParser::new(&[0x24, 0, 0]).parse().is_err());
+    }
+
+    #[test]
+    fn test_parse_relative_refs_with_base() {
+        // PtgRefN one row up and one column right, PtgAreaN over the row
+        // itself with an absolute first column, PtgAdd
+        let mut data = vec![0x2C];
+        data.extend_from_slice(&(-1i32).to_le_bytes());
+        data.extend_from_slice(&0xC001u16.to_le_bytes());
+        data.push(0x2D);
+        data.extend_from_slice(&0i32.to_le_bytes());
+        data.extend_from_slice(&0i32.to_le_bytes());
+        data.extend_from_slice(&0x8000u16.to_le_bytes());
+        data.extend_from_slice(&0xFFFEu16.to_le_bytes());
+        data.push(0x03);
+
+        let tokens = FormulaParser::new(&data).with_base(4, 2).parse().unwrap();
+        assert_eq!(FormulaConverter::tokens_to_string(&tokens), "D4+$A5:A5");
+
+        // Offsets wrap around the edges of the sheet
+        let tokens = FormulaParser::new(&data).with_base(0, 0).parse().unwrap();
+        assert_eq!(
+            FormulaConverter::tokens_to_string(&tokens),
+            "B1048576+$A1:XFC1"
+        );
     }
 }
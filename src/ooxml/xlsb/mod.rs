@@ -8,9 +8,10 @@
 //!
 //! ## Reading
 //! - **Cell Data**: Read all cell types including numbers, strings, booleans, errors, and formulas
-//! - **Formulas**: Parse formula records with cached values (FMLA_STRING, FMLA_NUM, FMLA_BOOL, FMLA_ERROR)
+//! - **Formulas**: Decode formula records (FMLA_STRING, FMLA_NUM, FMLA_BOOL, FMLA_ERROR) into formula text with cached values
 //! - **Styles**: Parse fonts, fills, borders, number formats, and cell formats from styles.bin
 //! - **Shared Strings**: Efficient shared string table parsing
+//! - **Rich Strings**: Read formatting runs and phonetic (furigana) text of shared and inline rich strings
 //! - **Workbook Structure**: Parse workbook properties, sheet metadata, and relationships
 //! - **Column Information**: Support for column widths, hidden columns, and custom widths
 //! - **Merged Cells**: Parse and handle merged cell ranges
@@ -29,7 +30,7 @@
 //! # Supported Record Types
 //!
 //! The module supports parsing of 100+ record types from the MS-XLSB specification, including:
-//! - Cell records (blank, RK, error, bool, real, string, ISST)
+//! - Cell records (blank, RK, error, bool, real, string, rich string, ISST)
 //! - Formula records (string, numeric, boolean, error)
 //! - Style records (fonts, fills, borders, number formats, XF)
 //! - Workbook structure records (sheets, properties, views)
@@ -117,6 +118,9 @@ mod cell;
 /// XLSB cells reader
 mod cells_reader;
 
+/// Lazy row iteration
+mod rows;
+
 /// Shared parsing utilities
 mod utils;
 
@@ -151,8 +155,12 @@ pub mod conditional_formatting;
 /// Formula parsing and generation
 pub mod formula;
 
+/// Rich string and phonetic text support
+pub mod rich_string;
+
 pub use cell::XlsbCell;
 pub use error::{XlsbError, XlsbResult};
+pub use rows::XlsbRowsIter;
 pub use styles_table::StylesTable;
 pub use workbook::XlsbWorkbook;
 pub use worksheet::XlsbWorksheet;
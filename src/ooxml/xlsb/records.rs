@@ -6,6 +6,7 @@
 
 use crate::common::binary;
 use crate::ooxml::xlsb::error::{XlsbError, XlsbResult};
+use crate::ooxml::xlsb::rich_string::RichString;
 use bytes::Bytes;
 use std::io::Read;
 
@@ -452,14 +453,17 @@ impl BundleSheetRecord {
 #[derive(Debug, Clone)]
 pub struct SstItemRecord {
     pub string: String,
+    /// Formatting runs and phonetic data, for rich items only
+    pub rich: Option<RichString>,
 }
 
 impl SstItemRecord {
     pub fn parse(data: &[u8]) -> XlsbResult<Self> {
-        let mut str_len = 0;
-        let string = wide_str(&data[1..], &mut str_len)?;
+        let (rich, _) = RichString::parse(data)?;
+        let string = rich.text.clone();
+        let rich = rich.is_rich().then_some(rich);
 
-        Ok(SstItemRecord { string })
+        Ok(SstItemRecord { string, rich })
     }
 }
 
@@ -945,10 +949,6 @@ impl<R: Read> RecordIter<R> {
         }
     }
 
-    pub fn from_cursor(cursor: std::io::Cursor<&[u8]>) -> RecordIter<std::io::Cursor<&[u8]>> {
-        RecordIter::new(cursor)
-    }
-
     fn read_u8(&mut self) -> Result<u8, std::io::Error> {
         self.reader.read_exact(&mut self.buffer)?;
         Ok(self.buffer[0])
@@ -0,0 +1,250 @@
+//! Rich string (RichStr) support for XLSB
+//!
+//! Shared string items (BrtSSTItem) and inline rich string cells
+//! (BrtCellRString) store their text as a RichStr: the plain text, optionally
+//! followed by formatting runs and a phonetic (furigana) block.
+
+use crate::common::binary;
+use crate::ooxml::xlsb::error::{XlsbError, XlsbResult};
+use crate::ooxml::xlsb::records::wide_str_with_len;
+
+/// fRichStr: formatting runs follow the text
+const FLAG_RICH: u8 = 0x01;
+/// fExtStr: phonetic text and runs follow
+const FLAG_PHONETIC: u8 = 0x02;
+
+/// Size of a StrRun structure (ich + ifnt)
+const STR_RUN_SIZE: usize = 4;
+/// Size of a PhRun structure (ichFirst + ichMom + cchMom + ifnt + phrun)
+const PH_RUN_SIZE: usize = 12;
+
+/// A formatting run of a rich string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RichTextRun {
+    /// First character of the run, in UTF-16 code units
+    pub start: u16,
+    /// Index of the font applied to the run in the styles font table
+    pub font_index: u16,
+}
+
+/// A phonetic (furigana) run of a rich string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PhoneticRun {
+    /// First character of the run in the phonetic text, in UTF-16 code units
+    pub start: u16,
+    /// First character of the base text the run annotates
+    pub base_start: u16,
+    /// Number of base text characters the run annotates
+    pub base_len: u16,
+    /// Index of the font used to display the run
+    pub font_index: u16,
+}
+
+/// Text of a shared string item or rich string cell
+///
+/// `text` is the text Excel displays; phonetic guides are kept separately.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RichString {
+    /// Displayed text
+    pub text: String,
+    /// Formatting runs, in text order (empty for plain strings)
+    pub runs: Vec<RichTextRun>,
+    /// Phonetic text, if the string has phonetic guides
+    pub phonetic_text: Option<String>,
+    /// Phonetic runs mapping `phonetic_text` onto `text`
+    pub phonetic_runs: Vec<PhoneticRun>,
+}
+
+impl RichString {
+    /// Create a plain string without runs or phonetic data
+    pub fn new(text: impl Into<String>) -> Self {
+        RichString {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Parse a RichStr structure and return it with the number of bytes consumed
+    ///
+    /// Runs that do not fit in `data` are dropped rather than reported as
+    /// errors, so a damaged run array never hides the text.
+    pub fn parse(data: &[u8]) -> XlsbResult<(Self, usize)> {
+        let Some(&flags) = data.first() else {
+            return Err(XlsbError::InvalidLength {
+                expected: 1,
+                found: 0,
+            });
+        };
+        let (text, text_len) = wide_str_with_len(&data[1..])?;
+        let mut pos = 1 + text_len;
+        let mut rich = RichString::new(text);
+
+        if flags & FLAG_RICH != 0 {
+            let (runs, len) = read_runs(&data[pos..], STR_RUN_SIZE, |run| RichTextRun {
+                start: binary::read_u16_le_at(run, 0).unwrap_or(0),
+                font_index: binary::read_u16_le_at(run, 2).unwrap_or(0),
+            });
+            rich.runs = runs;
+            pos += len;
+        }
+
+        if flags & FLAG_PHONETIC != 0
+            && let Ok((phonetic, len)) = wide_str_with_len(&data[pos..])
+        {
+            pos += len;
+            let (runs, len) = read_runs(&data[pos..], PH_RUN_SIZE, |run| PhoneticRun {
+                start: binary::read_u16_le_at(run, 0).unwrap_or(0),
+                base_start: binary::read_u16_le_at(run, 2).unwrap_or(0),
+                base_len: binary::read_u16_le_at(run, 4).unwrap_or(0),
+                font_index: binary::read_u16_le_at(run, 6).unwrap_or(0),
+            });
+            rich.phonetic_text = Some(phonetic);
+            rich.phonetic_runs = runs;
+            pos += len;
+        }
+
+        Ok((rich, pos))
+    }
+
+    /// Whether the string carries formatting runs or phonetic data
+    pub fn is_rich(&self) -> bool {
+        !self.runs.is_empty() || self.phonetic_text.is_some()
+    }
+
+    /// Split the text into its formatting runs
+    ///
+    /// Returns each run's text with its font index. Text before the first
+    /// run is omitted, as Excel formats it with the cell font.
+    pub fn segments(&self) -> Vec<(&str, u16)> {
+        let mut segments = Vec::with_capacity(self.runs.len());
+        for (i, run) in self.runs.iter().enumerate() {
+            let start = utf16_to_byte_offset(&self.text, run.start);
+            let end = self.runs.get(i + 1).map_or(self.text.len(), |next| {
+                utf16_to_byte_offset(&self.text, next.start)
+            });
+            if start < end {
+                segments.push((&self.text[start..end], run.font_index));
+            }
+        }
+        segments
+    }
+}
+
+/// Read a u32 count followed by fixed-size run structures
+fn read_runs<T>(data: &[u8], size: usize, parse: impl Fn(&[u8]) -> T) -> (Vec<T>, usize) {
+    let Ok(count) = binary::read_u32_le_at(data, 0) else {
+        return (Vec::new(), data.len());
+    };
+    let available = (data.len() - 4) / size;
+    let count = (count as usize).min(available);
+    let runs = data[4..4 + count * size]
+        .chunks_exact(size)
+        .map(parse)
+        .collect();
+    (runs, 4 + count * size)
+}
+
+/// Byte offset in `text` of the UTF-16 code unit offset `ich`, clamped to the text
+fn utf16_to_byte_offset(text: &str, ich: u16) -> usize {
+    let mut units = 0usize;
+    for (offset, ch) in text.char_indices() {
+        if units >= ich as usize {
+            return offset;
+        }
+        units += ch.len_utf16();
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn wide(text: &str) -> Vec<u8> {
+        let units: Vec<u16> = text.encode_utf16().collect();
+        let mut data = (units.len() as u32).to_le_bytes().to_vec();
+        for unit in units {
+            data.extend_from_slice(&unit.to_le_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_parse_plain() {
+        let mut data = vec![0];
+        data.extend(wide("Hello"));
+        let (rich, len) = RichString::parse(&data).unwrap();
+        assert_eq!(rich, RichString::new("Hello"));
+        assert_eq!(len, data.len());
+        assert!(!rich.is_rich());
+    }
+
+    #[test]
+    fn test_parse_runs_and_phonetic() {
+        let mut data = vec![FLAG_RICH | FLAG_PHONETIC];
+        data.extend(wide("東京都"));
+        data.extend(2u32.to_le_bytes());
+        data.extend([0, 0, 1, 0]);
+        data.extend([2, 0, 3, 0]);
+        data.extend(wide("とうきょうと"));
+        data.extend(1u32.to_le_bytes());
+        data.extend([0, 0, 0, 0, 2, 0, 4, 0, 0, 0, 0, 0]);
+        // Trailing bytes belong to the enclosing record
+        data.extend([0xFF; 4]);
+
+        let (rich, len) = RichString::parse(&data).unwrap();
+        assert_eq!(len, data.len() - 4);
+        assert_eq!(rich.text, "東京都");
+        assert_eq!(rich.segments(), vec![("東京", 1), ("都", 3)]);
+        assert_eq!(rich.phonetic_text.as_deref(), Some("とうきょうと"));
+        assert_eq!(
+            rich.phonetic_runs,
+            vec![PhoneticRun {
+                start: 0,
+                base_start: 0,
+                base_len: 2,
+                font_index: 4,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_truncated_runs() {
+        let mut data = vec![FLAG_RICH | FLAG_PHONETIC];
+        data.extend(wide("abc"));
+        data.extend(5u32.to_le_bytes());
+        data.extend([1, 0, 2, 0, 9]);
+
+        let (rich, len) = RichString::parse(&data).unwrap();
+        assert_eq!(rich.text, "abc");
+        assert_eq!(
+            rich.runs,
+            vec![RichTextRun {
+                start: 1,
+                font_index: 2,
+            }]
+        );
+        assert_eq!(rich.phonetic_text, None);
+        assert!(len <= data.len());
+        assert!(RichString::parse(&[]).is_err());
+    }
+
+    #[test]
+    fn test_segments_surrogate_pairs() {
+        let rich = RichString {
+            text: "😀ab".to_string(),
+            runs: vec![
+                RichTextRun {
+                    start: 0,
+                    font_index: 0,
+                },
+                RichTextRun {
+                    start: 2,
+                    font_index: 1,
+                },
+            ],
+            ..Default::default()
+        };
+        assert_eq!(rich.segments(), vec![("😀", 0), ("ab", 1)]);
+    }
+}
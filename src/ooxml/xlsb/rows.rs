@@ -0,0 +1,198 @@
+//! Lazy row iteration over XLSB worksheets.
+
+use std::io::Cursor;
+
+use crate::ooxml::xlsb::cells_reader::{CellsEvent, XlsbCellsReader};
+use crate::ooxml::xlsb::error::XlsbResult;
+use crate::sheet::{Row, RowValue};
+
+/// Streaming iterator over the rows of an XLSB worksheet.
+///
+/// Created by [`XlsbWorkbook::rows_iter`](super::XlsbWorkbook::rows_iter).
+/// Rows are yielded in record order with 0-based indices. Blank cells are
+/// left out of [`Row::cells`], and rows that only carry a header record are
+/// yielded with no cells.
+pub struct XlsbRowsIter<'a> {
+    reader: XlsbCellsReader<'a, Cursor<&'a [u8]>>,
+    /// Row header already read while finishing the previous row
+    pending_row: Option<u32>,
+    done: bool,
+}
+
+impl<'a> XlsbRowsIter<'a> {
+    pub(crate) fn new(reader: XlsbCellsReader<'a, Cursor<&'a [u8]>>) -> Self {
+        Self {
+            reader,
+            pending_row: None,
+            done: false,
+        }
+    }
+
+    /// Skip the next `n` rows without collecting their cells.
+    pub fn skip_rows(mut self, n: usize) -> Self {
+        for _ in 0..n {
+            match self.advance(false) {
+                Some(Ok(_)) => {},
+                Some(Err(_)) | None => break,
+            }
+        }
+        self
+    }
+
+    /// Read the next row, collecting its cells only if `collect` is set.
+    fn advance(&mut self, collect: bool) -> Option<XlsbResult<Row<'a>>> {
+        if self.done {
+            return None;
+        }
+
+        let result = self.read_row(collect);
+        match result {
+            Ok(Some(row)) => Some(Ok(row)),
+            Ok(None) => {
+                self.done = true;
+                None
+            },
+            Err(e) => {
+                self.done = true;
+                Some(Err(e))
+            },
+        }
+    }
+
+    fn read_row(&mut self, collect: bool) -> XlsbResult<Option<Row<'a>>> {
+        let index = match self.pending_row.take() {
+            Some(index) => index,
+            None => loop {
+                match self.reader.next_event()? {
+                    Some(CellsEvent::Row(index)) => break index,
+                    // Cells before the first row header have no row to belong to
+                    Some(CellsEvent::Cell(..)) => {},
+                    None => return Ok(None),
+                }
+            },
+        };
+
+        let mut cells = Vec::new();
+        loop {
+            match self.reader.next_event()? {
+                Some(CellsEvent::Row(next)) => {
+                    self.pending_row = Some(next);
+                    break;
+                },
+                Some(CellsEvent::Cell(col, value)) => {
+                    if collect && value != RowValue::Empty {
+                        cells.push((col, value));
+                    }
+                },
+                None => {
+                    self.done = true;
+                    break;
+                },
+            }
+        }
+
+        Ok(Some(Row::new(index, cells)))
+    }
+}
+
+impl<'a> Iterator for XlsbRowsIter<'a> {
+    type Item = XlsbResult<Row<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.advance(true)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            if let Err(e) = self.advance(false)? {
+                return Some(Err(e));
+            }
+        }
+        self.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ooxml::xlsb::XlsbWorkbook;
+    use crate::sheet::{CellValue, WorkbookTrait};
+
+    fn open(name: &str) -> XlsbWorkbook {
+        let file = std::fs::File::open(format!("test-data/ooxml/xlsb/{name}")).unwrap();
+        XlsbWorkbook::new(std::io::BufReader::new(file)).unwrap()
+    }
+
+    #[test]
+    fn rows_iter_matches_eager_worksheet() {
+        for name in [
+            "Simple.xlsb",
+            "date.xlsb",
+            "date1904.xlsb",
+            "universal-content.xlsb",
+            "rich-strings.xlsb",
+        ] {
+            let workbook = open(name);
+            for index in 0..workbook.worksheet_count() {
+                let eager = workbook.worksheet_by_index(index).unwrap();
+                let mut count = 0;
+                for row in workbook.rows_iter(index).unwrap() {
+                    let row = row.unwrap();
+                    let row_index = row.index();
+                    for (col, value) in row.into_cell_values() {
+                        let expected = eager.cell_value(row_index, col).unwrap();
+                        assert_eq!(&value, expected.as_ref(), "{name} sheet {index}");
+                        count += 1;
+                    }
+                }
+
+                let mut cells = eager.cells();
+                let mut non_empty = 0;
+                while let Some(cell) = cells.next() {
+                    if *cell.unwrap().value() != CellValue::Empty {
+                        non_empty += 1;
+                    }
+                }
+                assert_eq!(count, non_empty, "{name} sheet {index}");
+            }
+        }
+    }
+
+    #[test]
+    fn date_cells_use_1904_date_system() {
+        let workbook = open("date1904.xlsb");
+        assert!(workbook.is_1904_date_system());
+
+        let sheet = workbook.worksheet_by_index(0).unwrap();
+        let date = sheet.cell_value(0, 0).unwrap().into_owned();
+        assert!(matches!(date, CellValue::DateTime(_)), "{date:?}");
+
+        // The same serial lands four years and a day later than in date.xlsb
+        let base = open("date.xlsb");
+        let base = base.worksheet_by_index(0).unwrap();
+        let base = base.cell_value(0, 0).unwrap().as_datetime(false).unwrap();
+        let shifted = date.as_datetime(true).unwrap();
+        assert_eq!((shifted - base).num_days(), 1462);
+
+        let row = workbook.rows_iter(0).unwrap().next().unwrap().unwrap();
+        assert_eq!(row.into_cell_values().first(), Some(&(0, date)));
+    }
+
+    #[test]
+    fn rows_iter_skips_leading_rows() {
+        let workbook = open("universal-content.xlsb");
+        let indices: Vec<u32> = workbook
+            .rows_iter(0)
+            .unwrap()
+            .map(|row| row.unwrap().index())
+            .collect();
+        assert!(indices.len() > 2);
+
+        let mut skipped = workbook.rows_iter(0).unwrap().skip_rows(1);
+        assert_eq!(skipped.next().unwrap().unwrap().index(), indices[1]);
+        assert_eq!(
+            skipped.nth(indices.len() - 3).unwrap().unwrap().index(),
+            indices[indices.len() - 1]
+        );
+        assert!(skipped.next().is_none());
+    }
+}
@@ -8,6 +8,7 @@ use crate::common::binary;
 use crate::ooxml::xlsb::error::{XlsbError, XlsbResult};
 use crate::ooxml::xlsb::records::{XlsbRecordIter, record_types, wide_str_with_len};
 use crate::ooxml::xlsb::styles::{Alignment, Border};
+use crate::sheet::date_system::is_date_format_id;
 use std::collections::HashMap;
 use std::io::Read;
 
@@ -351,24 +352,14 @@ impl StylesTable {
         self.num_fmts.get(&id).map(|s| s.as_str())
     }
 
-    /// Check if a format code represents a date format
+    /// Check if a number format represents a date or time format
     pub fn is_date_format(&self, num_fmt_id: u32) -> bool {
-        // Built-in date formats
-        if matches!(num_fmt_id, 14..=22 | 27..=36 | 45..=47 | 50..=58) {
-            return true;
-        }
+        is_date_format_id(num_fmt_id, self.get_num_fmt(num_fmt_id))
+    }
 
-        // Custom format - check format code for date indicators
-        if let Some(format_code) = self.get_num_fmt(num_fmt_id) {
-            let format_lower = format_code.to_lowercase();
-            // Simple heuristic: contains date/time indicators
-            format_lower.contains('y')
-                || format_lower.contains('m')
-                || format_lower.contains('d')
-                || format_lower.contains('h')
-                || format_lower.contains('s')
-        } else {
-            false
-        }
+    /// Check if the cell format at `index` shows numbers as dates or times
+    pub fn is_date_style(&self, index: usize) -> bool {
+        self.get_cell_format(index)
+            .is_some_and(|xf| self.is_date_format(xf.num_fmt_id))
     }
 }
@@ -1,10 +1,16 @@
 //! Workbook implementation for XLSB files
 
+use crate::common::{Protection, ProtectionKind, binary};
 use crate::ooxml::opc::OpcPackage;
+use crate::ooxml::xlsb::cells_reader::XlsbCellsReader;
 use crate::ooxml::xlsb::error::XlsbResult;
-use crate::ooxml::xlsb::records::{XlsbRecordIter, record_types};
+use crate::ooxml::xlsb::records::{RecordIter, XlsbRecordIter, record_types};
+use crate::ooxml::xlsb::rich_string::RichString;
+use crate::ooxml::xlsb::rows::XlsbRowsIter;
+use crate::ooxml::xlsb::styles_table::StylesTable;
 use crate::ooxml::xlsb::worksheet::XlsbWorksheet;
-use crate::sheet::{Result, Worksheet as SheetTrait, WorksheetIterator};
+use crate::sheet::{DateSystem, Result, Worksheet as SheetTrait, WorksheetIterator};
+use std::collections::BTreeMap;
 use std::io::{BufReader, Cursor, Read, Seek};
 
 /// XLSB workbook implementation
@@ -13,7 +19,14 @@ pub struct XlsbWorkbook {
     package: OpcPackage,
     worksheets: Vec<XlsbWorksheet>,
     worksheet_names: Vec<String>,
+    /// Sheet name of each extern sheet table entry, for formula references
+    /// to other sheets (empty for entries outside this workbook)
+    extern_sheets: Vec<String>,
     shared_strings: Vec<String>,
+    /// Formatting runs and phonetic text of rich shared strings, by index
+    rich_shared_strings: BTreeMap<usize, RichString>,
+    /// Cell formats from xl/styles.bin
+    styles: StylesTable,
     is_1904: bool,
 }
 
@@ -35,12 +48,16 @@ impl XlsbWorkbook {
             package,
             worksheets: Vec::new(),
             worksheet_names: Vec::new(),
+            extern_sheets: Vec::new(),
             shared_strings: Vec::new(),
+            rich_shared_strings: BTreeMap::new(),
+            styles: StylesTable::default(),
             is_1904: false,
         };
 
         workbook.load_workbook_info()?;
         workbook.load_shared_strings()?;
+        workbook.load_styles()?;
 
         Ok(workbook)
     }
@@ -58,12 +75,16 @@ impl XlsbWorkbook {
             package,
             worksheets: Vec::new(),
             worksheet_names: Vec::new(),
+            extern_sheets: Vec::new(),
             shared_strings: Vec::new(),
+            rich_shared_strings: BTreeMap::new(),
+            styles: StylesTable::default(),
             is_1904: false,
         };
 
         workbook.load_workbook_info()?;
         workbook.load_shared_strings()?;
+        workbook.load_styles()?;
 
         Ok(workbook)
     }
@@ -73,9 +94,14 @@ impl XlsbWorkbook {
         let workbook_uri = crate::ooxml::opc::PackURI::new("/xl/workbook.bin")?;
         let workbook_part = self.package.get_part(&workbook_uri)?;
 
-        let blob = workbook_part.blob();
+        let blob = workbook_part.try_blob()?;
         let mut iter = XlsbRecordIter::new(BufReader::new(blob));
-        Self::read_workbook(&mut iter, &mut self.worksheet_names, &mut self.is_1904)?;
+        Self::read_workbook(
+            &mut iter,
+            &mut self.worksheet_names,
+            &mut self.extern_sheets,
+            &mut self.is_1904,
+        )?;
 
         Ok(())
     }
@@ -84,16 +110,122 @@ impl XlsbWorkbook {
     fn load_shared_strings(&mut self) -> XlsbResult<()> {
         let shared_strings_uri = crate::ooxml::opc::PackURI::new("/xl/sharedStrings.bin")?;
         if let Ok(shared_strings_part) = self.package.get_part(&shared_strings_uri) {
-            let blob = shared_strings_part.blob();
+            let blob = shared_strings_part.try_blob()?;
             let mut iter = XlsbRecordIter::new(BufReader::new(blob));
-            Self::read_shared_strings(&mut iter, &mut self.shared_strings)?;
+            Self::read_shared_strings(
+                &mut iter,
+                &mut self.shared_strings,
+                &mut self.rich_shared_strings,
+            )?;
         }
 
         Ok(())
     }
 
+    /// Load cell formats from xl/styles.bin
+    fn load_styles(&mut self) -> XlsbResult<()> {
+        let styles_uri = crate::ooxml::opc::PackURI::new("/xl/styles.bin")?;
+        if let Ok(styles_part) = self.package.get_part(&styles_uri) {
+            self.styles = StylesTable::from_reader(BufReader::new(styles_part.try_blob()?))?;
+        }
+
+        Ok(())
+    }
+
+    /// Get the date system of the workbook
+    fn date_system(&self) -> DateSystem {
+        DateSystem::from_1904_flag(self.is_1904)
+    }
+
     /// Get a worksheet by index (lazy loading)
     fn get_worksheet(&self, index: usize) -> XlsbResult<XlsbWorksheet> {
+        let blob = self.worksheet_blob(index)?;
+        let name = &self.worksheet_names[index];
+        let cells_reader =
+            XlsbCellsReader::new(RecordIter::new(Cursor::new(blob)), &self.shared_strings)?
+                .with_extern_sheets(&self.extern_sheets)
+                .with_rich_strings(&self.rich_shared_strings)
+                .with_date_styles(&self.styles, self.date_system());
+        Self::read_worksheet(cells_reader, name.clone())
+    }
+
+    /// Stream the rows of the worksheet at `index` without loading it.
+    ///
+    /// Rows are decoded one at a time from the binary part and shared strings
+    /// are borrowed rather than cloned, so memory use does not grow with the
+    /// number of cells. Row and column indices are 0-based, as in
+    /// [`XlsbWorksheet`]. Random access still requires loading the worksheet
+    /// through [`WorkbookTrait::worksheet_by_index`](crate::sheet::WorkbookTrait::worksheet_by_index).
+    pub fn rows_iter(&self, index: usize) -> XlsbResult<XlsbRowsIter<'_>> {
+        let blob = self.worksheet_blob(index)?;
+        let iter = RecordIter::new(Cursor::new(blob));
+        let reader = XlsbCellsReader::new(iter, &self.shared_strings)?
+            .with_extern_sheets(&self.extern_sheets)
+            .with_date_styles(&self.styles, self.date_system());
+        Ok(XlsbRowsIter::new(reader))
+    }
+
+    /// Get the editing protection of the workbook.
+    ///
+    /// Combines the read-only recommendation and password to modify
+    /// (BrtFileSharing) and the structure and revision locks
+    /// (BrtBookProtection) of workbook.bin with the protection of every
+    /// worksheet (BrtSheetProtection). A locked structure, window layout or
+    /// sheet makes the workbook read-only.
+    pub fn protection(&self) -> XlsbResult<Protection> {
+        let workbook_uri = crate::ooxml::opc::PackURI::new("/xl/workbook.bin")?;
+        let mut protection =
+            Self::read_protection(self.package.get_part(&workbook_uri)?.try_blob()?)?;
+        for index in 0..self.worksheet_names.len() {
+            if let Ok(blob) = self.worksheet_blob(index) {
+                protection = protection.or(Self::read_protection(blob)?);
+            }
+        }
+        Ok(protection)
+    }
+
+    /// Read the protection records of a workbook or worksheet part
+    fn read_protection(blob: &[u8]) -> XlsbResult<Protection> {
+        let mut protection = Protection::default();
+        for record in XlsbRecordIter::new(BufReader::new(blob)) {
+            let record = record?;
+            let data = &record.data;
+            let u16_at = |offset| binary::read_u16_le_at(data, offset).unwrap_or(0);
+            let found = match record.header.record_type {
+                record_types::FILE_SHARING if u16_at(2) != 0 => {
+                    Protection::with_password(ProtectionKind::ReadOnly)
+                },
+                record_types::FILE_SHARING if u16_at(0) != 0 => {
+                    Protection::new(ProtectionKind::ReadOnlyRecommended)
+                },
+                // protpwdBook, protpwdRev, then fLockStructure, fLockWindow
+                // and fLockRevision
+                record_types::BOOK_PROTECTION if u16_at(4) & 0x03 != 0 => Protection {
+                    kind: ProtectionKind::ReadOnly,
+                    has_password: u16_at(0) != 0,
+                },
+                record_types::BOOK_PROTECTION if u16_at(4) & 0x04 != 0 => Protection {
+                    kind: ProtectionKind::TrackedChanges,
+                    has_password: u16_at(2) != 0,
+                },
+                // protpwd, then fLocked
+                record_types::SHEET_PROTECTION
+                    if binary::read_u32_le_at(data, 2).unwrap_or(0) != 0 =>
+                {
+                    Protection {
+                        kind: ProtectionKind::ReadOnly,
+                        has_password: u16_at(0) != 0,
+                    }
+                },
+                _ => continue,
+            };
+            protection = protection.or(found);
+        }
+        Ok(protection)
+    }
+
+    /// Get the binary part of the worksheet at `index`.
+    fn worksheet_blob(&self, index: usize) -> XlsbResult<&[u8]> {
         if index >= self.worksheet_names.len() {
             return Err(crate::ooxml::error::OoxmlError::InvalidFormat(format!(
                 "Worksheet index {} out of bounds",
@@ -102,21 +234,19 @@ impl XlsbWorkbook {
             .into());
         }
 
-        let name = &self.worksheet_names[index];
         // For now, assume worksheets are at xl/worksheets/sheet1.bin, sheet2.bin, etc.
         let sheet_path = format!("/xl/worksheets/sheet{}.bin", index + 1);
         let sheet_uri = crate::ooxml::opc::PackURI::new(&sheet_path)?;
 
         let sheet_part = self.package.get_part(&sheet_uri)?;
-        let blob = sheet_part.blob();
-        let cursor = Cursor::new(blob);
-        Self::read_worksheet(cursor, name.clone(), &self.shared_strings)
+        Ok(sheet_part.try_blob()?)
     }
 
     /// Read shared strings from SST
     fn read_shared_strings(
         iter: &mut XlsbRecordIter<impl Read>,
         strings: &mut Vec<String>,
+        rich_strings: &mut BTreeMap<usize, RichString>,
     ) -> XlsbResult<()> {
         for record in iter.by_ref() {
             let record = record?;
@@ -125,10 +255,15 @@ impl XlsbWorkbook {
                     // SST header, continue reading
                 },
                 record_types::SST_ITEM => {
-                    if let Ok(sst_item) =
-                        crate::ooxml::xlsb::records::SstItemRecord::parse(&record.data)
-                    {
-                        strings.push(sst_item.string);
+                    // Keep indices aligned with BrtCellIsst even if an item is damaged
+                    match crate::ooxml::xlsb::records::SstItemRecord::parse(&record.data) {
+                        Ok(sst_item) => {
+                            if let Some(rich) = sst_item.rich {
+                                rich_strings.insert(strings.len(), rich);
+                            }
+                            strings.push(sst_item.string);
+                        },
+                        Err(_) => strings.push(String::new()),
                     }
                 },
                 record_types::END_SST => {
@@ -146,8 +281,14 @@ impl XlsbWorkbook {
     fn read_workbook(
         iter: &mut XlsbRecordIter<impl Read>,
         worksheet_names: &mut Vec<String>,
+        extern_sheets: &mut Vec<String>,
         is_1904: &mut bool,
     ) -> XlsbResult<()> {
+        // Supporting links (external workbooks, add-ins, this workbook) are
+        // numbered in order of appearance; extern sheet entries refer to them
+        let mut supporting_links = 0u32;
+        let mut self_link = None;
+
         for record in iter.by_ref() {
             let record = record?;
             match record.header.record_type {
@@ -168,7 +309,18 @@ impl XlsbWorkbook {
                         },
                     }
                 },
-                record_types::END_BUNDLE_SHS => {
+                record_types::BEGIN_SUP_BOOK => {
+                    supporting_links += 1;
+                },
+                record_types::SUP_SELF | record_types::SUP_SAME => {
+                    self_link = Some(supporting_links);
+                    supporting_links += 1;
+                },
+                record_types::EXTERN_SHEET => {
+                    *extern_sheets =
+                        Self::parse_extern_sheet(&record.data, self_link, worksheet_names);
+                },
+                record_types::END_BOOK => {
                     break;
                 },
                 _ => {
@@ -179,17 +331,51 @@ impl XlsbWorkbook {
         Ok(())
     }
 
+    /// Resolve a BrtExternSheet record to the sheet name of each entry
+    ///
+    /// Each entry (XTI) holds a supporting link index and the first and last
+    /// sheet it covers. Entries that point outside this workbook, or at no
+    /// sheet (-1 for `#REF!`, -2 for the workbook itself), get an empty name.
+    fn parse_extern_sheet(
+        data: &[u8],
+        self_link: Option<u32>,
+        worksheet_names: &[String],
+    ) -> Vec<String> {
+        let count = binary::read_u32_le_at(data, 0).unwrap_or(0) as usize;
+        let sheet_name = |tab: i32| {
+            usize::try_from(tab)
+                .ok()
+                .and_then(|tab| worksheet_names.get(tab))
+        };
+
+        data.get(4..)
+            .unwrap_or_default()
+            .chunks_exact(12)
+            .take(count)
+            .map(|xti| {
+                let link = binary::read_u32_le_at(xti, 0).unwrap_or(u32::MAX);
+                let first = binary::read_u32_le_at(xti, 4).map_or(-1, |v| v as i32);
+                let last = binary::read_u32_le_at(xti, 8).map_or(-1, |v| v as i32);
+                if Some(link) != self_link {
+                    return String::new();
+                }
+                match (sheet_name(first), sheet_name(last)) {
+                    (Some(first_name), _) if first == last => first_name.clone(),
+                    (Some(first_name), Some(last_name)) => {
+                        format!("{}:{}", first_name, last_name)
+                    },
+                    _ => String::new(),
+                }
+            })
+            .collect()
+    }
+
     /// Read a worksheet
     fn read_worksheet(
-        cursor: Cursor<&[u8]>,
+        mut cells_reader: XlsbCellsReader<'_, Cursor<&[u8]>>,
         name: String,
-        shared_strings: &[String],
     ) -> XlsbResult<XlsbWorksheet> {
         let mut worksheet = XlsbWorksheet::new(name);
-        let iter =
-            crate::ooxml::xlsb::records::RecordIter::<std::io::Cursor<&[u8]>>::from_cursor(cursor);
-        let mut cells_reader =
-            crate::ooxml::xlsb::cells_reader::XlsbCellsReader::new(iter, shared_strings.to_vec())?;
 
         // Read all cells
         while let Some(cell) = cells_reader.next_cell()? {
@@ -203,9 +389,17 @@ impl XlsbWorkbook {
         for hyperlink in cells_reader.hyperlinks {
             worksheet.add_hyperlink(hyperlink);
         }
+        for ((row, col), rich) in cells_reader.rich_strings {
+            worksheet.add_rich_string(row, col, rich);
+        }
 
         Ok(worksheet)
     }
+
+    /// Get the OPC package
+    pub(crate) fn package(&self) -> &OpcPackage {
+        &self.package
+    }
 }
 
 impl crate::sheet::WorkbookTrait for XlsbWorkbook {
@@ -227,6 +421,12 @@ impl crate::sheet::WorkbookTrait for XlsbWorkbook {
     }
 
     fn worksheet_by_index(&self, index: usize) -> Result<Box<dyn SheetTrait + '_>> {
+        if index >= self.worksheet_names.len() {
+            return Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
+                index
+            ))));
+        }
         let worksheet = self.get_worksheet(index)?;
         Ok(Box::new(worksheet))
     }
@@ -237,9 +437,10 @@ impl crate::sheet::WorkbookTrait for XlsbWorkbook {
                 return self.worksheet_by_index(i);
             }
         }
-        Err(Box::new(crate::ooxml::error::OoxmlError::InvalidFormat(
-            format!("Worksheet '{}' not found", name),
-        )))
+        Err(Box::new(crate::common::Error::InvalidReference(format!(
+            "Worksheet '{}' not found",
+            name
+        ))))
     }
 
     fn worksheets<'a>(&'a self) -> Box<dyn WorksheetIterator<'a> + 'a> {
@@ -277,3 +478,70 @@ impl<'a> WorksheetIterator<'a> for XlsbWorksheetIterator<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sheet::{Cell, CellValue};
+
+    #[test]
+    fn test_rich_and_phonetic_strings() {
+        let file = std::fs::File::open("test-data/ooxml/xlsb/rich-strings.xlsb").unwrap();
+        let workbook = XlsbWorkbook::new(BufReader::new(file)).unwrap();
+        let sheet = workbook.get_worksheet(0).unwrap();
+
+        let text = |row| match sheet.get_cell(row, 0).map(|cell| cell.value()) {
+            Some(CellValue::String(text)) => text.as_str(),
+            other => panic!("row {row}: {other:?}"),
+        };
+        // Phonetic guides are not part of the displayed text
+        assert_eq!(text(0), "東京");
+        assert_eq!(text(1), "Bold and plain");
+        assert_eq!(text(2), "漢字かな");
+        assert_eq!(text(3), "inline text");
+
+        let shared = sheet.rich_string(0, 0).unwrap();
+        assert_eq!(shared.phonetic_text.as_deref(), Some("とうきょう"));
+        assert_eq!(shared.phonetic_runs[0].base_len, 2);
+
+        let runs = sheet.rich_string(1, 0).unwrap();
+        assert_eq!(runs.segments(), vec![("Bold", 1), (" and plain", 0)]);
+
+        let inline = sheet.rich_string(2, 0).unwrap();
+        assert_eq!(inline.segments(), vec![("漢字", 2), ("かな", 0)]);
+        assert_eq!(inline.phonetic_text.as_deref(), Some("かんじ"));
+
+        assert!(sheet.rich_string(3, 0).is_none());
+    }
+
+    #[test]
+    fn test_read_protection() {
+        use crate::ooxml::xlsb::writer::RecordWriter;
+
+        let records = |records: &[(u16, &[u8])]| {
+            let mut data = Vec::new();
+            {
+                let mut writer = RecordWriter::new(&mut data);
+                for (record_type, payload) in records {
+                    writer.write_record(*record_type, payload).unwrap();
+                }
+                writer.flush().unwrap();
+            }
+            data
+        };
+        // Read-only recommended, no password to modify
+        let file_sharing: (u16, &[u8]) = (record_types::FILE_SHARING, &[1, 0, 0, 0, 0, 0, 0, 0]);
+        // Structure locked with a password
+        let book_protection: (u16, &[u8]) =
+            (record_types::BOOK_PROTECTION, &[0x4B, 0xCE, 0, 0, 1, 0]);
+
+        assert_eq!(
+            XlsbWorkbook::read_protection(&records(&[file_sharing])).unwrap(),
+            Protection::new(ProtectionKind::ReadOnlyRecommended)
+        );
+        assert_eq!(
+            XlsbWorkbook::read_protection(&records(&[file_sharing, book_protection])).unwrap(),
+            Protection::with_password(ProtectionKind::ReadOnly)
+        );
+    }
+}
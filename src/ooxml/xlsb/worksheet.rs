@@ -4,6 +4,7 @@ use crate::ooxml::xlsb::cell::XlsbCell;
 use crate::ooxml::xlsb::comments::Comment;
 use crate::ooxml::xlsb::hyperlinks::Hyperlink;
 use crate::ooxml::xlsb::merged_cells::MergedCell;
+use crate::ooxml::xlsb::rich_string::RichString;
 use crate::sheet::{Cell as SheetCell, CellIterator, CellValue, Result, RowIterator, Worksheet};
 use std::borrow::Cow;
 use std::collections::BTreeMap;
@@ -18,6 +19,7 @@ pub struct XlsbWorksheet {
     merged_cells: Vec<MergedCell>,
     hyperlinks: Vec<Hyperlink>,
     comments: Vec<Comment>,
+    rich_strings: BTreeMap<(u32, u32), RichString>,
 }
 
 impl XlsbWorksheet {
@@ -31,6 +33,7 @@ impl XlsbWorksheet {
             merged_cells: Vec::new(),
             hyperlinks: Vec::new(),
             comments: Vec::new(),
+            rich_strings: BTreeMap::new(),
         }
     }
 
@@ -62,6 +65,17 @@ impl XlsbWorksheet {
         self.comments.push(comment);
     }
 
+    /// Set the rich string (formatting runs and phonetic text) of a cell
+    pub fn add_rich_string(&mut self, row: u32, col: u32, rich: RichString) {
+        self.rich_strings.insert((row, col), rich);
+    }
+
+    /// Get the rich string of a cell, if its text has formatting runs or
+    /// phonetic text
+    pub fn rich_string(&self, row: u32, col: u32) -> Option<&RichString> {
+        self.rich_strings.get(&(row, col))
+    }
+
     /// Get all merged cells
     pub fn merged_cells(&self) -> &[MergedCell] {
         &self.merged_cells
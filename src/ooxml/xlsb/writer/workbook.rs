@@ -2,7 +2,12 @@
 //!
 //! This module provides functionality to create complete XLSB files with multiple worksheets,
 //! shared strings, styles, and advanced features.
+use crate::common::Metadata;
 use crate::common::xml::escape_xml;
+use crate::ooxml::common::DocumentProperties;
+use crate::ooxml::common::properties::write_property_part;
+use crate::ooxml::custom_properties::CustomProperties;
+use crate::ooxml::opc::constants::content_type as ct;
 use crate::ooxml::opc::constants::relationship_type as rel;
 use crate::ooxml::opc::part::Part;
 use crate::ooxml::opc::{BlobPart, OpcPackage, PackURI};
@@ -46,6 +51,8 @@ pub struct XlsbWorkbookWriter {
     shared_strings: MutableSharedStringsWriter,
     styles: StylesWriter,
     is_1904: bool,
+    properties: DocumentProperties,
+    custom_properties: CustomProperties,
 }
 
 /// Minimal Worksheet Binary Index payload for an empty worksheet.
@@ -74,6 +81,10 @@ impl XlsbWorkbookWriter {
             shared_strings: MutableSharedStringsWriter::new(),
             styles: StylesWriter::new(),
             is_1904: false,
+            properties: DocumentProperties::new()
+                .creator("The Litchi Rust Library")
+                .last_modified_by("The Litchi Rust Library"),
+            custom_properties: CustomProperties::new(),
         }
     }
 
@@ -126,6 +137,30 @@ impl XlsbWorkbookWriter {
         &mut self.styles
     }
 
+    /// Get a reference to the document properties
+    pub fn properties(&self) -> &DocumentProperties {
+        &self.properties
+    }
+
+    /// Get a mutable reference to the document properties
+    ///
+    /// Creation and modification dates left unset are filled in with the
+    /// current time when the workbook is saved.
+    pub fn properties_mut(&mut self) -> &mut DocumentProperties {
+        &mut self.properties
+    }
+
+    /// Get a mutable reference to the custom document properties
+    pub fn custom_properties_mut(&mut self) -> &mut CustomProperties {
+        &mut self.custom_properties
+    }
+
+    /// Replace the document properties and custom properties with `metadata`
+    pub fn set_metadata(&mut self, metadata: &Metadata) {
+        self.properties = DocumentProperties::from(metadata);
+        self.custom_properties = CustomProperties::from(&metadata.custom_properties);
+    }
+
     /// Save the workbook to a writer
     ///
     /// # Arguments
@@ -201,32 +236,36 @@ impl XlsbWorkbookWriter {
     /// Add document properties (required by Excel to open the file)
     fn add_doc_props(&self, package: &mut OpcPackage) -> XlsbResult<()> {
         // Add app.xml (Extended Properties)
-        let app_xml = self.create_app_xml();
-        let app_uri = PackURI::new("/docProps/app.xml")?;
-        let app_part = BlobPart::new(
-            app_uri,
-            "application/vnd.openxmlformats-officedocument.extended-properties+xml".to_string(),
-            app_xml.into_bytes(),
-        );
-        package.add_part(Box::new(app_part));
-        package.relate_to(
-            "docProps/app.xml",
-            "http://schemas.openxmlformats.org/officeDocument/2006/relationships/extended-properties",
-        );
+        let app_xml = self.properties.merge_app_xml(&self.create_app_xml())?;
+        write_property_part(
+            package,
+            rel::EXTENDED_PROPERTIES,
+            "/docProps/app.xml",
+            ct::OFC_EXTENDED_PROPERTIES,
+            |_| Ok(app_xml),
+        )?;
 
         // Add core.xml (Core Properties)
         let core_xml = self.create_core_xml();
-        let core_uri = PackURI::new("/docProps/core.xml")?;
-        let core_part = BlobPart::new(
-            core_uri,
-            "application/vnd.openxmlformats-package.core-properties+xml".to_string(),
-            core_xml.into_bytes(),
-        );
-        package.add_part(Box::new(core_part));
-        package.relate_to(
-            "docProps/core.xml",
-            "http://schemas.openxmlformats.org/package/2006/relationships/metadata/core-properties",
-        );
+        write_property_part(
+            package,
+            rel::CORE_PROPERTIES,
+            "/docProps/core.xml",
+            ct::OPC_CORE_PROPERTIES,
+            |_| Ok(core_xml),
+        )?;
+
+        // Add custom.xml (Custom Properties) when any are set
+        if !self.custom_properties.is_empty() {
+            let custom_xml = self.custom_properties.to_xml()?;
+            write_property_part(
+                package,
+                rel::CUSTOM_PROPERTIES,
+                "/docProps/custom.xml",
+                ct::OFC_CUSTOM_PROPERTIES,
+                |_| Ok(custom_xml),
+            )?;
+        }
 
         Ok(())
     }
@@ -277,28 +316,12 @@ impl XlsbWorkbookWriter {
     }
 
     /// Create core.xml content (Core Properties)
+    ///
+    /// Missing creation and modification dates default to the current time.
     fn create_core_xml(&self) -> String {
-        // Get current timestamp in W3CDTF format
-        let now = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap();
-        let timestamp = format_w3cdtf(now.as_secs());
-
-        xml_minifier::minified_xml_format!(
-            r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
-                <cp:coreProperties
-                    xmlns:cp="http://schemas.openxmlformats.org/package/2006/metadata/core-properties"
-                    xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:dcterms="http://purl.org/dc/terms/"
-                    xmlns:dcmitype="http://purl.org/dc/dcmitype/"
-                    xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance">
-                    <dc:creator>The Litchi Rust Library</dc:creator>
-                    <cp:lastModifiedBy>The Litchi Rust Library</cp:lastModifiedBy>
-                    <dcterms:created xsi:type="dcterms:W3CDTF">{}</dcterms:created>
-                    <dcterms:modified xsi:type="dcterms:W3CDTF">{}</dcterms:modified>
-                </cp:coreProperties>"#,
-            timestamp,
-            timestamp
-        )
+        let mut properties = self.properties.clone();
+        properties.stamp();
+        properties.to_xml()
     }
 
     /// Add theme (REQUIRED by Excel to open file)
@@ -598,7 +621,8 @@ impl XlsbWorkbookWriter {
         temp_writer.write_i32(-1)?;
         temp_writer.write_i32(-1)?;
 
-        // Then for each sheet: (0, sheet_index, sheet_index)
+        // Then for each sheet: (0, sheet_index, sheet_index), so sheet i is
+        // referenced from formulas as ixti i + FIRST_SHEET_IXTI
         for i in 0..sheet_count {
             temp_writer.write_u32(0)?;
             temp_writer.write_i32(i as i32)?;
@@ -615,6 +639,14 @@ impl XlsbWorkbookWriter {
 
     /// Add worksheet parts to the package
     fn add_worksheet_parts(&mut self, package: &mut OpcPackage) -> XlsbResult<()> {
+        // Formulas refer to other sheets through the extern sheet table,
+        // which lists the worksheets in this order
+        let sheet_names: Vec<String> = self
+            .worksheets
+            .iter()
+            .map(|worksheet| worksheet.name().to_string())
+            .collect();
+
         for (i, worksheet) in self.worksheets.iter_mut().enumerate() {
             // Create the worksheet part with an empty blob first so we can attach
             // relationships (binary index + external hyperlinks) and obtain
@@ -666,7 +698,7 @@ impl XlsbWorkbookWriter {
             let mut sheet_data = Vec::new();
             {
                 let mut writer = RecordWriter::new(&mut sheet_data);
-                worksheet.write(&mut writer, &mut self.shared_strings)?;
+                worksheet.write(&mut writer, &mut self.shared_strings, &sheet_names)?;
             }
             sheet_part.set_blob(sheet_data);
 
@@ -716,27 +748,6 @@ impl XlsbWorkbookWriter {
     }
 }
 
-/// Format Unix timestamp as W3CDTF (ISO 8601)
-fn format_w3cdtf(secs: u64) -> String {
-    // Simple conversion: seconds since 1970-01-01 to ISO 8601
-    // This is a simplified version; for production, use chrono or time crate
-    let days = secs / 86400;
-    let year = 1970 + (days / 365);
-    let day_of_year = days % 365;
-    let month = ((day_of_year / 30) + 1).min(12);
-    let day = ((day_of_year % 30) + 1).min(31);
-
-    let time_of_day = secs % 86400;
-    let hours = time_of_day / 3600;
-    let minutes = (time_of_day % 3600) / 60;
-    let seconds = time_of_day % 60;
-
-    format!(
-        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
-        year, month, day, hours, minutes, seconds
-    )
-}
-
 impl Default for XlsbWorkbookWriter {
     fn default() -> Self {
         Self::new()
@@ -851,11 +862,30 @@ mod tests {
     }
 
     #[test]
-    fn test_format_w3cdtf() {
-        let timestamp = format_w3cdtf(0); // Unix epoch
-        assert!(timestamp.contains("T"));
-        assert!(timestamp.ends_with("Z"));
-        assert!(timestamp.starts_with("1970-"));
+    fn test_doc_props_use_properties() {
+        let mut workbook = XlsbWorkbookWriter::new();
+        workbook.properties_mut().title = Some("Ledger".to_string());
+        workbook.properties_mut().company = Some("Acme".to_string());
+
+        let mut package = OpcPackage::new();
+        workbook.add_doc_props(&mut package).unwrap();
+
+        let core = package
+            .get_part(&PackURI::new("/docProps/core.xml").unwrap())
+            .unwrap();
+        let core = std::str::from_utf8(core.blob()).unwrap();
+        assert!(core.contains("<dc:title>Ledger</dc:title>"));
+        assert!(core.contains("<dc:creator>The Litchi Rust Library</dc:creator>"));
+        assert!(core.contains(r#"<dcterms:created xsi:type="dcterms:W3CDTF">"#));
+
+        let app = package
+            .get_part(&PackURI::new("/docProps/app.xml").unwrap())
+            .unwrap();
+        assert!(
+            std::str::from_utf8(app.blob())
+                .unwrap()
+                .contains("<Company>Acme</Company>")
+        );
     }
 
     #[test]
@@ -873,4 +903,91 @@ mod tests {
         workbook.add_named_range(named_range);
         // Verify it was added (indirectly via the test not failing)
     }
+
+    #[test]
+    fn test_formulas_round_trip_through_reader() {
+        use crate::ooxml::xlsb::XlsbWorkbook;
+        use crate::sheet::{CellValue, WorkbookTrait};
+
+        let mut data = MutableXlsbWorksheet::new("Data");
+        data.set_cell(0, 0, 21.0);
+        data.set_cell_formula(0, 2, "=A1*2+SUM(B1:B10)").unwrap();
+        data.set_cell_formula_with_value(1, 2, "$A$1&\"!\"", "21!")
+            .unwrap();
+        data.set_cell_formula_with_value(2, 2, "'Q1 Totals'!B$2>A1", true)
+            .unwrap();
+        data.set_cell_formula_with_value(3, 2, "1/0", CellValue::Error("#DIV/0!".into()))
+            .unwrap();
+
+        let mut totals = MutableXlsbWorksheet::new("Q1 Totals");
+        totals
+            .set_cell_formula_with_value(1, 1, "SUM(Data!$A1:A$3)*'Q1 Totals'!$C$1", 42.0)
+            .unwrap();
+
+        let mut workbook = XlsbWorkbookWriter::new();
+        workbook.add_worksheet(data);
+        workbook.add_worksheet(totals);
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        workbook.save(&mut bytes).unwrap();
+
+        let workbook = XlsbWorkbook::new(std::io::Cursor::new(bytes.into_inner())).unwrap();
+        let formula_at = |sheet: usize, row: u32, col: u32| {
+            let worksheet = workbook.worksheet_by_index(sheet).unwrap();
+            assert!(worksheet.cell(row, col).unwrap().is_formula());
+            match worksheet.cell_value(row, col).unwrap().into_owned() {
+                CellValue::Formula {
+                    formula,
+                    cached_value,
+                    ..
+                } => (formula, cached_value.map(|value| *value)),
+                other => panic!("expected a formula, got {other:?}"),
+            }
+        };
+
+        assert_eq!(
+            formula_at(0, 0, 2),
+            ("A1*2+SUM(B1:B10)".to_string(), Some(CellValue::Float(0.0)))
+        );
+        assert_eq!(
+            formula_at(0, 1, 2),
+            (
+                "$A$1&\"!\"".to_string(),
+                Some(CellValue::String("21!".to_string()))
+            )
+        );
+        assert_eq!(
+            formula_at(0, 2, 2),
+            (
+                "'Q1 Totals'!B$2>A1".to_string(),
+                Some(CellValue::Bool(true))
+            )
+        );
+        assert_eq!(
+            formula_at(0, 3, 2),
+            (
+                "1/0".to_string(),
+                Some(CellValue::Error("#DIV/0!".to_string()))
+            )
+        );
+        assert_eq!(
+            formula_at(1, 1, 1),
+            (
+                "SUM(Data!$A1:A$3)*'Q1 Totals'!$C$1".to_string(),
+                Some(CellValue::Float(42.0))
+            )
+        );
+    }
+
+    #[test]
+    fn test_formula_with_unknown_sheet_fails_to_save() {
+        let mut sheet = MutableXlsbWorksheet::new("Sheet1");
+        sheet.set_cell_formula(0, 0, "Missing!A1").unwrap();
+
+        let mut workbook = XlsbWorkbookWriter::new();
+        workbook.add_worksheet(sheet);
+        let result = workbook.save(std::io::Cursor::new(Vec::new()));
+        assert!(
+            matches!(result, Err(crate::ooxml::xlsb::XlsbError::WorksheetNotFound(name)) if name == "Missing")
+        );
+    }
 }
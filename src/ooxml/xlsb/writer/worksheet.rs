@@ -4,6 +4,7 @@ use crate::ooxml::xlsb::comments::Comment;
 use crate::ooxml::xlsb::conditional_formatting::ConditionalFormatting;
 use crate::ooxml::xlsb::data_validation::DataValidation;
 use crate::ooxml::xlsb::error::XlsbResult;
+use crate::ooxml::xlsb::formula::FormulaEncoder;
 use crate::ooxml::xlsb::hyperlinks::Hyperlink;
 use crate::ooxml::xlsb::merged_cells::MergedCell;
 use crate::ooxml::xlsb::records::record_types;
@@ -179,6 +180,64 @@ impl MutableXlsbWorksheet {
         self.max_col = self.max_col.max(col);
     }
 
+    /// Set a cell formula, with or without a leading `=`
+    ///
+    /// The formula is compiled into Excel's parsed formula tokens when the
+    /// workbook is saved; its syntax is checked here. References to other
+    /// sheets (`Sheet2!A1`, `'My Sheet'!$A$1:$B$2`) must name a worksheet of
+    /// the workbook by then. No cached result is stored, so the cell reads as
+    /// 0 until Excel recalculates the workbook.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use litchi::ooxml::xlsb::writer::MutableXlsbWorksheet;
+    ///
+    /// let mut sheet = MutableXlsbWorksheet::new("Sheet1");
+    /// sheet.set_cell_formula(0, 2, "A1*2+SUM(B1:B10)")?;
+    /// ```
+    pub fn set_cell_formula(&mut self, row: u32, col: u32, formula: &str) -> XlsbResult<()> {
+        self.set_formula(row, col, formula, None)
+    }
+
+    /// Set a cell formula along with its cached result
+    ///
+    /// The cached value is what readers that do not calculate formulas see.
+    pub fn set_cell_formula_with_value<V: Into<CellValue>>(
+        &mut self,
+        row: u32,
+        col: u32,
+        formula: &str,
+        cached_value: V,
+    ) -> XlsbResult<()> {
+        self.set_formula(row, col, formula, Some(cached_value.into()))
+    }
+
+    fn set_formula(
+        &mut self,
+        row: u32,
+        col: u32,
+        formula: &str,
+        cached_value: Option<CellValue>,
+    ) -> XlsbResult<()> {
+        let formula = formula.strip_prefix('=').unwrap_or(formula);
+        FormulaEncoder::syntax_only().encode(formula)?;
+
+        let style = self.cells.get(&(row, col)).map_or(0, |cell| cell.style);
+        self.set_cell_with_style(
+            row,
+            col,
+            CellValue::Formula {
+                formula: formula.to_string(),
+                cached_value: cached_value.map(Box::new),
+                is_array: false,
+                array_range: None,
+            },
+            style,
+        );
+        Ok(())
+    }
+
     /// Get a cell value
     pub fn get_cell(&self, row: u32, col: u32) -> Option<&CellValue> {
         self.cells.get(&(row, col)).map(|c| &c.value)
@@ -499,11 +558,13 @@ impl MutableXlsbWorksheet {
 
     /// Write worksheet to binary format
     ///
-    /// Following Excel's required structure
+    /// Following Excel's required structure. `sheet_names` lists the
+    /// workbook's worksheets in order, for formulas referring to other sheets.
     pub(crate) fn write<W: Write>(
         &self,
         writer: &mut RecordWriter<W>,
         shared_strings: &mut crate::ooxml::xlsb::writer::MutableSharedStringsWriter,
+        sheet_names: &[String],
     ) -> XlsbResult<()> {
         // Write BrtBeginSheet
         writer.write_record(record_types::BEGIN_SHEET, &[])?;
@@ -525,7 +586,7 @@ impl MutableXlsbWorksheet {
 
         // Write sheet data
         writer.write_record(record_types::BEGIN_SHEET_DATA, &[])?;
-        self.write_cells(writer, shared_strings)?;
+        self.write_cells(writer, shared_strings, &FormulaEncoder::new(sheet_names))?;
         writer.write_record(record_types::END_SHEET_DATA, &[])?;
 
         // Sheet protection (BrtSheetProtection) - minimal skeleton mirroring
@@ -723,6 +784,7 @@ impl MutableXlsbWorksheet {
         &self,
         writer: &mut RecordWriter<W>,
         shared_strings: &mut crate::ooxml::xlsb::writer::MutableSharedStringsWriter,
+        formulas: &FormulaEncoder<'_>,
     ) -> XlsbResult<()> {
         let mut current_row: Option<u32> = None;
 
@@ -734,7 +796,7 @@ impl MutableXlsbWorksheet {
             }
 
             // Write cell
-            self.write_cell(writer, *row, *col, cell_data, shared_strings)?;
+            self.write_cell(writer, *row, *col, cell_data, shared_strings, formulas)?;
         }
 
         Ok(())
@@ -845,6 +907,7 @@ impl MutableXlsbWorksheet {
         col: u32,
         cell_data: &CellData,
         shared_strings: &mut crate::ooxml::xlsb::writer::MutableSharedStringsWriter,
+        formulas: &FormulaEncoder<'_>,
     ) -> XlsbResult<()> {
         match &cell_data.value {
             CellValue::Empty => self.write_blank_cell(writer, col, cell_data.style)?,
@@ -860,43 +923,19 @@ impl MutableXlsbWorksheet {
                 // CellValue::DateTime stores the Excel serial number directly
                 self.write_number_cell(writer, col, *dt, cell_data.style)?;
             },
-            CellValue::Formula { cached_value, .. } => {
-                // For formulas, write the cached value
-                // TODO: Support writing formula bytes
-                if let Some(cached) = cached_value {
-                    match cached.as_ref() {
-                        CellValue::Empty => self.write_blank_cell(writer, col, cell_data.style)?,
-                        CellValue::String(s) => self.write_shared_string_cell(
-                            writer,
-                            col,
-                            s,
-                            cell_data.style,
-                            shared_strings,
-                        )?,
-                        CellValue::Int(i) => {
-                            self.write_number_cell(writer, col, *i as f64, cell_data.style)?
-                        },
-                        CellValue::Float(f) => {
-                            self.write_number_cell(writer, col, *f, cell_data.style)?
-                        },
-                        CellValue::Bool(b) => {
-                            self.write_bool_cell(writer, col, *b, cell_data.style)?
-                        },
-                        CellValue::Error(e) => {
-                            self.write_error_cell(writer, col, e, cell_data.style)?
-                        },
-                        CellValue::DateTime(dt) => {
-                            self.write_number_cell(writer, col, *dt, cell_data.style)?
-                        },
-                        CellValue::Formula { .. } => {
-                            // Nested formula - shouldn't happen, but write as blank
-                            self.write_blank_cell(writer, col, cell_data.style)?;
-                        },
-                    }
-                } else {
-                    // No cached value, write as blank
-                    self.write_blank_cell(writer, col, cell_data.style)?;
-                }
+            CellValue::Formula {
+                formula,
+                cached_value,
+                ..
+            } => {
+                let rgce = formulas.encode(formula)?;
+                self.write_formula_cell(
+                    writer,
+                    col,
+                    &rgce,
+                    cached_value.as_deref(),
+                    cell_data.style,
+                )?;
             },
         }
         Ok(())
@@ -1011,17 +1050,7 @@ impl MutableXlsbWorksheet {
         error: &str,
         style: u32,
     ) -> XlsbResult<()> {
-        let error_code = match error {
-            "#NULL!" => 0x00,
-            "#DIV/0!" => 0x07,
-            "#VALUE!" => 0x0F,
-            "#REF!" => 0x17,
-            "#NAME?" => 0x1D,
-            "#NUM!" => 0x24,
-            "#N/A" => 0x2A,
-            "#GETTING_DATA" => 0x2B,
-            _ => 0x2A, // Default to #N/A
-        };
+        let error_code = error_code(error);
 
         let mut data = Vec::new();
         let mut temp_writer = RecordWriter::new(&mut data);
@@ -1034,6 +1063,63 @@ impl MutableXlsbWorksheet {
         Ok(())
     }
 
+    /// Write a formula cell (BrtFmlaNum / BrtFmlaString / BrtFmlaBool / BrtFmlaError)
+    ///
+    /// Layout: Cell (8 bytes) + cached value + grbitFlags (2 bytes) +
+    /// CellParsedFormula (cce u32 + rgce + cb u32 + rgcb). The record type
+    /// follows the cached value; formulas without one are written as
+    /// BrtFmlaNum with a 0 result.
+    fn write_formula_cell<W: Write>(
+        &self,
+        writer: &mut RecordWriter<W>,
+        col: u32,
+        rgce: &[u8],
+        cached_value: Option<&CellValue>,
+        style: u32,
+    ) -> XlsbResult<()> {
+        let mut data = Vec::new();
+        let mut temp_writer = RecordWriter::new(&mut data);
+
+        Self::write_cell_structure(&mut temp_writer, col, style)?;
+        let record_type = match cached_value {
+            Some(CellValue::String(s)) => {
+                temp_writer.write_wide_string(s)?;
+                record_types::FMLA_STRING
+            },
+            Some(CellValue::Bool(b)) => {
+                temp_writer.write_u8(*b as u8)?;
+                record_types::FMLA_BOOL
+            },
+            Some(CellValue::Error(e)) => {
+                temp_writer.write_u8(error_code(e))?;
+                record_types::FMLA_ERROR
+            },
+            Some(CellValue::Int(i)) => {
+                temp_writer.write_f64(*i as f64)?;
+                record_types::FMLA_NUM
+            },
+            Some(CellValue::Float(f) | CellValue::DateTime(f)) => {
+                temp_writer.write_f64(*f)?;
+                record_types::FMLA_NUM
+            },
+            Some(CellValue::Empty | CellValue::Formula { .. }) | None => {
+                temp_writer.write_f64(0.0)?;
+                record_types::FMLA_NUM
+            },
+        };
+
+        // grbitFlags: no always-calc or shared-formula flags
+        temp_writer.write_u16(0)?;
+
+        // CellParsedFormula without extra data (no array constants)
+        temp_writer.write_u32(rgce.len() as u32)?;
+        temp_writer.inner_mut().extend_from_slice(rgce);
+        temp_writer.write_u32(0)?;
+
+        writer.write_record(record_type, &data)?;
+        Ok(())
+    }
+
     /// Write merged cells
     fn write_merged_cells<W: Write>(&self, writer: &mut RecordWriter<W>) -> XlsbResult<()> {
         // BrtBeginMergeCells (0x00B1) payload is a single DWORD count of BrtMergeCell
@@ -1178,6 +1264,21 @@ impl MutableXlsbWorksheet {
     }
 }
 
+/// Error code (BErr) of an error value
+fn error_code(error: &str) -> u8 {
+    match error {
+        "#NULL!" => 0x00,
+        "#DIV/0!" => 0x07,
+        "#VALUE!" => 0x0F,
+        "#REF!" => 0x17,
+        "#NAME?" => 0x1D,
+        "#NUM!" => 0x24,
+        "#N/A" => 0x2A,
+        "#GETTING_DATA" => 0x2B,
+        _ => 0x2A, // Default to #N/A
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1447,7 +1548,7 @@ mod tests {
         let mut writer = RecordWriter::new(&mut buffer);
         let mut shared_strings = crate::ooxml::xlsb::writer::MutableSharedStringsWriter::new();
 
-        let result = sheet.write(&mut writer, &mut shared_strings);
+        let result = sheet.write(&mut writer, &mut shared_strings, &[]);
         assert!(result.is_ok());
         assert!(!buffer.is_empty());
     }
@@ -1463,7 +1564,7 @@ mod tests {
         let mut writer = RecordWriter::new(&mut buffer);
         let mut shared_strings = crate::ooxml::xlsb::writer::MutableSharedStringsWriter::new();
 
-        let result = sheet.write(&mut writer, &mut shared_strings);
+        let result = sheet.write(&mut writer, &mut shared_strings, &[]);
         assert!(result.is_ok());
         assert!(!buffer.is_empty());
 
@@ -19,6 +19,7 @@ use crate::ooxml::charts::{
     },
 };
 use crate::ooxml::error::{OoxmlError, Result};
+use crate::ooxml::xlsx::cell::Cell;
 
 /// Chart anchor position in a worksheet.
 ///
@@ -72,6 +73,49 @@ impl ChartAnchor {
         }
     }
 
+    /// Create a chart anchor covering a cell range such as `"E2:L18"`.
+    ///
+    /// The chart spans from the top-left corner of the first cell to the
+    /// bottom-right corner of the last cell.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use litchi::ooxml::xlsx::ChartAnchor;
+    ///
+    /// let anchor = ChartAnchor::from_range("B2:H15").unwrap();
+    /// assert_eq!((anchor.from_col, anchor.from_row), (1, 1));
+    /// assert_eq!((anchor.to_col, anchor.to_row), (8, 15));
+    /// ```
+    pub fn from_range(range: &str) -> Result<Self> {
+        let invalid =
+            || OoxmlError::InvalidFormat(format!("Invalid chart anchor range: {}", range));
+        let range = range.replace('$', "");
+        let (first, last) = range.split_once(':').unwrap_or((&range, &range));
+        let is_cell = |cell: &str| {
+            let letters = cell.chars().take_while(char::is_ascii_alphabetic).count();
+            (1..=3).contains(&letters)
+                && cell.len() > letters
+                && cell[letters..].chars().all(|c| c.is_ascii_digit())
+        };
+        if !is_cell(first) || !is_cell(last) {
+            return Err(invalid());
+        }
+
+        let (first_col, first_row) = Cell::reference_to_coords(first).map_err(|_| invalid())?;
+        let (last_col, last_row) = Cell::reference_to_coords(last).map_err(|_| invalid())?;
+        if first_row == 0 || last_row == 0 {
+            return Err(invalid());
+        }
+
+        Ok(Self::new(
+            first_col.min(last_col) - 1,
+            first_row.min(last_row) - 1,
+            first_col.max(last_col),
+            first_row.max(last_row),
+        ))
+    }
+
     /// Create a chart anchor with precise offsets.
     #[allow(clippy::too_many_arguments)]
     pub fn with_offsets(
@@ -483,3 +527,195 @@ pub fn generate_chart_xml(chart: &ChartModel) -> Result<Vec<u8>> {
         .map_err(|e| OoxmlError::Xml(e.to_string()))?;
     Ok(output)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::xlsx::Workbook;
+    use std::io::Read;
+
+    /// Read a part of a saved package as text.
+    fn read_part(data: &[u8], name: &str) -> String {
+        let archive = soapberry_zip::office::ArchiveReader::new(data).unwrap();
+        let mut text = String::new();
+        archive
+            .read_stream(name)
+            .unwrap()
+            .read_to_string(&mut text)
+            .unwrap();
+        text
+    }
+
+    #[test]
+    fn chart_anchor_from_range() {
+        let anchor = ChartAnchor::from_range("$E$2:$L$18").unwrap();
+        assert_eq!((anchor.from_col, anchor.from_row), (4, 1));
+        assert_eq!((anchor.to_col, anchor.to_row), (12, 18));
+
+        // Reversed corners and a single cell
+        let anchor = ChartAnchor::from_range("C5:A1").unwrap();
+        assert_eq!((anchor.from_col, anchor.from_row), (0, 0));
+        assert_eq!((anchor.to_col, anchor.to_row), (3, 5));
+        let anchor = ChartAnchor::from_range("B2").unwrap();
+        assert_eq!((anchor.to_col, anchor.to_row), (2, 2));
+
+        assert!(ChartAnchor::from_range("Sheet1").is_err());
+        assert!(ChartAnchor::from_range("A0:B2").is_err());
+    }
+
+    #[test]
+    fn add_chart_binds_series_to_cells() {
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.add_worksheet("Sales Data");
+        ws.set_cell_value(1, 1, "Month");
+        ws.set_cell_value(1, 2, "Revenue");
+        for (row, (month, revenue)) in [("Jan", 120.0), ("Feb", 95.5), ("Mar", 143.0)]
+            .iter()
+            .enumerate()
+        {
+            ws.set_cell_value(row as u32 + 2, 1, *month);
+            ws.set_cell_value(row as u32 + 2, 2, *revenue);
+        }
+        // Comments are related to the sheet before the drawing
+        ws.set_cell_comment(1, 2, "Net of returns", "Finance");
+
+        let mut bars = BarTypeGroup::new(BarDirection::Column, BarGrouping::Clustered);
+        bars.common.add_series(
+            Series::new(0)
+                .with_categories(StringData::from_ref("$A$2:$A$4"))
+                .with_values(NumericData::from_ref("$B$2:$B$4")),
+        );
+        bars.common.series[0].title = Some(TitleText::from_ref("$B$1"));
+        let chart = ChartModel::new().with_title("Revenue").with_plot_area(
+            PlotArea::new()
+                .add_type_group(TypeGroup::Bar(bars))
+                .add_axis(Axis::Category(CategoryAxis::new(
+                    1,
+                    AxisPosition::Bottom,
+                    2,
+                )))
+                .add_axis(Axis::Value(ValueAxis::new(2, AxisPosition::Left, 1))),
+        );
+        ws.add_chart(chart, "D2:J16").unwrap();
+        assert!(ws.add_chart(ChartModel::new(), "nowhere").is_err());
+        assert_eq!(ws.charts().len(), 1);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sales.xlsx");
+        wb.save(&path).unwrap();
+        let data = std::fs::read(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&data);
+
+        // The worksheet points at its drawing relationship, not the first one
+        let sheet_xml = read_part(&data, "xl/worksheets/sheet2.xml");
+        let rels = read_part(&data, "xl/worksheets/_rels/sheet2.xml.rels");
+        let start = sheet_xml.find("<drawing r:id=\"").unwrap() + 15;
+        let rel_id = &sheet_xml[start..start + sheet_xml[start..].find('"').unwrap()];
+        let rel = rels
+            .split("<Relationship ")
+            .find(|rel| rel.contains(&format!("Id=\"{}\"", rel_id)))
+            .unwrap();
+        assert!(rel.contains("../drawings/drawing2.xml"));
+
+        let chart_xml = read_part(&data, "xl/charts/chart2000.xml");
+        assert!(chart_xml.contains("<c:f>&apos;Sales Data&apos;!$B$2:$B$4</c:f><c:numCache>"));
+        assert!(chart_xml.contains("<c:f>&apos;Sales Data&apos;!$B$1</c:f>"));
+        assert!(chart_xml.contains(r#"<c:pt idx="1"><c:v>95.5</c:v></c:pt>"#));
+
+        let wb = Workbook::open(&path).unwrap();
+        let charts = wb.charts().unwrap();
+        assert_eq!(charts.len(), 1);
+        let series = charts[0].plot_area.series().next().unwrap();
+        match &series.title {
+            Some(TitleText::Literal(text)) => assert_eq!(text.text, "Revenue"),
+            other => panic!("unexpected series title: {:?}", other),
+        }
+        assert_eq!(
+            series.categories.as_ref().unwrap().values,
+            vec!["Jan", "Feb", "Mar"]
+        );
+        assert_eq!(
+            series.values.as_ref().unwrap().values,
+            vec![120.0, 95.5, 143.0]
+        );
+    }
+
+    #[test]
+    fn workbook_charts_round_trip() {
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        ws.set_cell_value(1, 1, "Region");
+        ws.set_cell_value(1, 2, "Sales");
+        for (row, (region, sales)) in [("North", 10.0), ("South", 20.0), ("East", 30.0)]
+            .iter()
+            .enumerate()
+        {
+            ws.set_cell_value(row as u32 + 2, 1, *region);
+            ws.set_cell_value(row as u32 + 2, 2, *sales);
+            ws.set_cell_value(row as u32 + 2, 3, row as f64 + 1.0);
+        }
+
+        let mut bar = WorksheetChart::bar_chart_with_cache(
+            "Sales",
+            "Sheet1!$A$2:$A$4",
+            &["North", "South", "East"],
+            "Sheet1!$B$2:$B$4",
+            &[10.0, 20.0, 30.0],
+            ChartAnchor::new(4, 1, 10, 15),
+        )
+        .unwrap();
+        bar.chart.plot_area.series_mut().next().unwrap().title =
+            Some(TitleText::from_ref("Sheet1!$B$1"));
+        ws.add_worksheet_chart(bar);
+
+        // No caches: values must be resolved from the cells.
+        let scatter = WorksheetChart::scatter_chart(
+            "Trend",
+            "Sheet1!$C$2:$C$4",
+            "Sheet1!$B$2:$B$4",
+            ChartAnchor::new(4, 16, 10, 30),
+        )
+        .unwrap();
+        ws.add_worksheet_chart(scatter);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("charts.xlsx");
+        wb.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let wb = Workbook::open(&path).unwrap();
+        let charts = wb.charts().unwrap();
+        assert_eq!(charts.len(), 2);
+
+        let bar = &charts[0];
+        assert!(matches!(bar.plot_area.type_groups[0], TypeGroup::Bar(_)));
+        let series = bar.plot_area.series().next().unwrap();
+        match &series.title {
+            Some(TitleText::Literal(text)) => assert_eq!(text.text, "Sales"),
+            other => panic!("unexpected series title: {:?}", other),
+        }
+        assert_eq!(
+            series.categories.as_ref().unwrap().values,
+            vec!["North", "South", "East"]
+        );
+        assert_eq!(
+            series.values.as_ref().unwrap().values,
+            vec![10.0, 20.0, 30.0]
+        );
+
+        let scatter = &charts[1];
+        assert!(matches!(
+            scatter.plot_area.type_groups[0],
+            TypeGroup::Scatter(_)
+        ));
+        let series = scatter.plot_area.series().next().unwrap();
+        assert_eq!(
+            series.x_values.as_ref().unwrap().values,
+            vec![1.0, 2.0, 3.0]
+        );
+        assert_eq!(
+            series.y_values.as_ref().unwrap().values,
+            vec![10.0, 20.0, 30.0]
+        );
+    }
+}
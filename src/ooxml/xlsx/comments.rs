@@ -0,0 +1,434 @@
+//! Unified worksheet comment model for XLSX files.
+//!
+//! Excel stores cell comments in two different ways:
+//!
+//! - **Notes** (legacy comments) live in `xl/comments*.xml` and carry a single
+//!   author and a rich-text body.
+//! - **Threaded comments** (Office 365) live in `xl/threadedComments/*.xml`,
+//!   reference their authors through `xl/persons/person.xml` and support
+//!   replies, timestamps and resolution state.
+//!
+//! When a workbook contains threaded comments, Excel also writes a legacy
+//! "bridge" note for each thread so older readers can show something. This
+//! module merges both sources into [`WorksheetComment`] values and drops the
+//! bridge notes that shadow a thread.
+
+use std::collections::HashMap;
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use crate::sheet::Result as SheetResult;
+
+use super::threaded_comments::{Mention, PersonList, ThreadedComments};
+use super::worksheet::Comment;
+
+/// A comment attached to a worksheet cell.
+///
+/// The variant tells whether the comment is a legacy note or a threaded
+/// conversation, so renderers can decide whether to show replies.
+#[derive(Debug, Clone)]
+pub enum WorksheetComment {
+    /// Legacy note from `xl/comments*.xml`.
+    Note(Comment),
+    /// Threaded conversation from `xl/threadedComments/*.xml`.
+    Thread(CommentThread),
+}
+
+impl WorksheetComment {
+    /// Cell reference the comment is anchored to (e.g., "A1").
+    pub fn cell_ref(&self) -> &str {
+        match self {
+            Self::Note(note) => &note.cell_ref,
+            Self::Thread(thread) => &thread.cell_ref,
+        }
+    }
+
+    /// Author of the note, or of the first comment in the thread.
+    pub fn author(&self) -> Option<&str> {
+        match self {
+            Self::Note(note) => note.author.as_deref(),
+            Self::Thread(thread) => thread.root.author.as_deref(),
+        }
+    }
+
+    /// Text of the note, or of the first comment in the thread.
+    pub fn text(&self) -> &str {
+        match self {
+            Self::Note(note) => &note.text,
+            Self::Thread(thread) => &thread.root.text,
+        }
+    }
+
+    /// Creation timestamp, if known.
+    ///
+    /// Legacy notes carry no timestamp, so this is always `None` for them.
+    pub fn timestamp(&self) -> Option<&str> {
+        match self {
+            Self::Note(_) => None,
+            Self::Thread(thread) => thread.root.timestamp.as_deref(),
+        }
+    }
+
+    /// Replies to the comment, in document order.
+    ///
+    /// Always empty for legacy notes.
+    pub fn replies(&self) -> &[ThreadedCommentEntry] {
+        match self {
+            Self::Note(_) => &[],
+            Self::Thread(thread) => &thread.replies,
+        }
+    }
+
+    /// Whether this is a threaded comment.
+    pub fn is_threaded(&self) -> bool {
+        matches!(self, Self::Thread(_))
+    }
+}
+
+/// A threaded comment conversation anchored to one cell.
+#[derive(Debug, Clone)]
+pub struct CommentThread {
+    /// Cell reference (e.g., "A1")
+    pub cell_ref: String,
+    /// The comment that started the thread
+    pub root: ThreadedCommentEntry,
+    /// Replies to the root comment, in document order
+    pub replies: Vec<ThreadedCommentEntry>,
+    /// Whether the thread has been marked as resolved
+    pub done: bool,
+}
+
+/// A single comment within a thread, with its author resolved.
+#[derive(Debug, Clone)]
+pub struct ThreadedCommentEntry {
+    /// Unique identifier of the comment
+    pub id: String,
+    /// Person ID of the author (key into the workbook's person list)
+    pub person_id: String,
+    /// Display name of the author, resolved from `persons.xml`
+    pub author: Option<String>,
+    /// Comment text
+    pub text: String,
+    /// Timestamp as stored in the file (ISO 8601)
+    pub timestamp: Option<String>,
+    /// @mentions in the comment text
+    pub mentions: Vec<Mention>,
+}
+
+/// Parse a legacy `xl/comments*.xml` part into notes.
+///
+/// Rich-text runs are flattened into plain text. Excel prefixes note bodies
+/// with a bold `"Author:"` run; that prefix is stripped so `text` holds only
+/// what the user typed.
+pub fn parse_comments_xml(xml: &str) -> SheetResult<Vec<Comment>> {
+    let mut reader = Reader::from_str(xml);
+
+    let mut authors: Vec<String> = Vec::new();
+    let mut comments = Vec::new();
+
+    let mut in_author = false;
+    let mut in_text = false;
+    let mut in_t = false;
+    let mut in_rph = false;
+    let mut current_author = String::new();
+    let mut current: Option<(String, Option<usize>)> = None;
+    let mut runs: Vec<String> = Vec::new();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(e)) => match e.local_name().as_ref() {
+                b"author" => {
+                    in_author = true;
+                    current_author.clear();
+                },
+                b"comment" => {
+                    let mut cell_ref = String::new();
+                    let mut author_id = None;
+                    for attr in e.attributes().flatten() {
+                        let value = attr.decode_and_unescape_value(reader.decoder())?;
+                        match attr.key.local_name().as_ref() {
+                            b"ref" => cell_ref = value.into_owned(),
+                            b"authorId" => author_id = value.parse::<usize>().ok(),
+                            _ => {},
+                        }
+                    }
+                    current = Some((cell_ref, author_id));
+                    runs.clear();
+                },
+                b"text" => in_text = true,
+                b"rPh" => in_rph = true,
+                b"t" if in_text && !in_rph => {
+                    in_t = true;
+                    runs.push(String::new());
+                },
+                _ => {},
+            },
+            Ok(Event::Text(e)) => {
+                let text = e.decode()?;
+                if in_author {
+                    current_author.push_str(&text);
+                } else if in_t && let Some(run) = runs.last_mut() {
+                    run.push_str(&text);
+                }
+            },
+            Ok(Event::GeneralRef(e)) => {
+                let resolved = match e.resolve_char_ref()? {
+                    Some(ch) => ch.to_string(),
+                    None => {
+                        let name = e.decode()?;
+                        quick_xml::escape::resolve_predefined_entity(&name)
+                            .unwrap_or_default()
+                            .to_string()
+                    },
+                };
+                if in_author {
+                    current_author.push_str(&resolved);
+                } else if in_t && let Some(run) = runs.last_mut() {
+                    run.push_str(&resolved);
+                }
+            },
+            Ok(Event::End(e)) => match e.local_name().as_ref() {
+                b"author" => {
+                    in_author = false;
+                    authors.push(std::mem::take(&mut current_author));
+                },
+                b"t" => in_t = false,
+                b"rPh" => in_rph = false,
+                b"text" => in_text = false,
+                b"comment" => {
+                    if let Some((cell_ref, author_id)) = current.take() {
+                        let author = author_id.and_then(|id| authors.get(id)).cloned();
+                        let text = note_text(&runs, author.as_deref());
+                        comments.push(Comment {
+                            cell_ref,
+                            author,
+                            text,
+                        });
+                    }
+                },
+                _ => {},
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Failed to parse comments XML: {}", e).into()),
+            _ => {},
+        }
+    }
+
+    Ok(comments)
+}
+
+/// Join the runs of a note body, dropping Excel's leading `"Author:"` run.
+fn note_text(runs: &[String], author: Option<&str>) -> String {
+    if let (Some(first), Some(author)) = (runs.first(), author)
+        && runs.len() > 1
+        && first.strip_suffix(':') == Some(author)
+    {
+        let text = runs[1..].concat();
+        return match text.strip_prefix('\n') {
+            Some(rest) => rest.to_string(),
+            None => text,
+        };
+    }
+
+    runs.concat()
+}
+
+/// Group threaded comments into conversations and resolve their authors.
+///
+/// Replies are attached to the thread of their `parentId`. Replies whose
+/// parent cannot be found are kept as stand-alone threads rather than being
+/// dropped.
+pub fn build_comment_threads(
+    comments: &ThreadedComments,
+    persons: Option<&PersonList>,
+) -> Vec<CommentThread> {
+    let names: HashMap<&str, &str> = persons
+        .map(|list| {
+            list.persons
+                .iter()
+                .map(|p| (p.id.as_str(), p.display_name.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut threads: Vec<CommentThread> = Vec::new();
+    let mut thread_by_id: HashMap<&str, usize> = HashMap::new();
+
+    for comment in &comments.comments {
+        let entry = ThreadedCommentEntry {
+            id: comment.id.clone(),
+            person_id: comment.person_id.clone(),
+            author: names.get(comment.person_id.as_str()).map(|s| s.to_string()),
+            text: comment.text.clone().unwrap_or_default(),
+            timestamp: comment.date_time.clone(),
+            mentions: comment.mentions.clone(),
+        };
+
+        let parent = comment
+            .parent_id
+            .as_deref()
+            .and_then(|id| thread_by_id.get(id).copied());
+
+        match parent {
+            Some(index) => {
+                thread_by_id.insert(comment.id.as_str(), index);
+                let thread = &mut threads[index];
+                if comment.done == Some(true) {
+                    thread.done = true;
+                }
+                thread.replies.push(entry);
+            },
+            None => {
+                thread_by_id.insert(comment.id.as_str(), threads.len());
+                threads.push(CommentThread {
+                    cell_ref: comment.cell_ref.clone().unwrap_or_default(),
+                    root: entry,
+                    replies: Vec::new(),
+                    done: comment.done.unwrap_or(false),
+                });
+            },
+        }
+    }
+
+    threads
+}
+
+/// Merge legacy notes and threaded conversations into one list.
+///
+/// Notes anchored to a cell that also has a thread are the backwards
+/// compatibility copies Excel writes for threaded comments, so they are
+/// skipped. The result is sorted by cell position.
+pub fn merge_comments(notes: Vec<Comment>, threads: Vec<CommentThread>) -> Vec<WorksheetComment> {
+    let threaded_cells: std::collections::HashSet<String> =
+        threads.iter().map(|t| t.cell_ref.clone()).collect();
+
+    let mut merged: Vec<WorksheetComment> = notes
+        .into_iter()
+        .filter(|note| !threaded_cells.contains(&note.cell_ref))
+        .map(WorksheetComment::Note)
+        .chain(threads.into_iter().map(WorksheetComment::Thread))
+        .collect();
+
+    merged.sort_by_key(|c| cell_sort_key(c.cell_ref()));
+    merged
+}
+
+/// Sort key (row, column) for an A1-style reference.
+fn cell_sort_key(cell_ref: &str) -> (u32, u32) {
+    super::cell::Cell::reference_to_coords(cell_ref)
+        .map(|(col, row)| (row, col))
+        .unwrap_or((u32::MAX, u32::MAX))
+}
+
+impl From<&crate::ooxml::xlsb::comments::Comment> for WorksheetComment {
+    fn from(comment: &crate::ooxml::xlsb::comments::Comment) -> Self {
+        let cell_ref = format!(
+            "{}{}",
+            super::cell::Cell::column_to_letters(comment.col + 1),
+            comment.row + 1
+        );
+        WorksheetComment::Note(Comment {
+            cell_ref,
+            author: Some(comment.author.clone()).filter(|a| !a.is_empty()),
+            text: comment.text.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::xlsx::threaded_comments::{Person, ThreadedComment};
+
+    #[test]
+    fn parses_legacy_notes_and_strips_author_prefix() {
+        let xml = r#"<?xml version="1.0"?><comments xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><authors><author>Alice</author><author>Bob &amp; Co</author></authors><commentList><comment ref="B3" authorId="1"><text><r><rPr><b/></rPr><t>Bob &amp; Co:</t></r><r><t xml:space="preserve">
+Check &lt;this&gt;</t></r></text></comment><comment ref="A1" authorId="0"><text><t>Plain</t></text></comment></commentList></comments>"#;
+
+        let notes = parse_comments_xml(xml).unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes[0].cell_ref, "B3");
+        assert_eq!(notes[0].author.as_deref(), Some("Bob & Co"));
+        assert_eq!(notes[0].text, "Check <this>");
+        assert_eq!(notes[1].author.as_deref(), Some("Alice"));
+        assert_eq!(notes[1].text, "Plain");
+    }
+
+    #[test]
+    fn builds_threads_with_replies_and_authors() {
+        let comments = ThreadedComments {
+            comments: vec![
+                ThreadedComment {
+                    cell_ref: Some("C2".to_string()),
+                    id: "{1}".to_string(),
+                    person_id: "{P1}".to_string(),
+                    text: Some("Root".to_string()),
+                    date_time: Some("2024-01-01T10:00:00.00".to_string()),
+                    ..Default::default()
+                },
+                ThreadedComment {
+                    cell_ref: Some("C2".to_string()),
+                    id: "{2}".to_string(),
+                    parent_id: Some("{1}".to_string()),
+                    person_id: "{P2}".to_string(),
+                    text: Some("Reply".to_string()),
+                    done: Some(true),
+                    ..Default::default()
+                },
+            ],
+        };
+        let persons = PersonList {
+            persons: vec![Person {
+                display_name: "Alice".to_string(),
+                id: "{P1}".to_string(),
+                user_id: None,
+                provider_id: None,
+            }],
+        };
+
+        let threads = build_comment_threads(&comments, Some(&persons));
+        assert_eq!(threads.len(), 1);
+        assert_eq!(threads[0].root.author.as_deref(), Some("Alice"));
+        assert_eq!(threads[0].replies.len(), 1);
+        assert_eq!(threads[0].replies[0].text, "Reply");
+        assert_eq!(threads[0].replies[0].author, None);
+        assert!(threads[0].done);
+    }
+
+    #[test]
+    fn merge_skips_bridge_notes() {
+        let notes = vec![
+            Comment {
+                cell_ref: "C2".to_string(),
+                author: Some("tc={1}".to_string()),
+                text: "Comment: Root".to_string(),
+            },
+            Comment {
+                cell_ref: "A1".to_string(),
+                author: Some("Alice".to_string()),
+                text: "Note".to_string(),
+            },
+        ];
+        let threads = vec![CommentThread {
+            cell_ref: "C2".to_string(),
+            root: ThreadedCommentEntry {
+                id: "{1}".to_string(),
+                person_id: "{P1}".to_string(),
+                author: None,
+                text: "Root".to_string(),
+                timestamp: None,
+                mentions: Vec::new(),
+            },
+            replies: Vec::new(),
+            done: false,
+        }];
+
+        let merged = merge_comments(notes, threads);
+        assert_eq!(merged.len(), 2);
+        assert!(!merged[0].is_threaded());
+        assert_eq!(merged[0].cell_ref(), "A1");
+        assert!(merged[1].is_threaded());
+        assert_eq!(merged[1].text(), "Root");
+    }
+}
@@ -0,0 +1,891 @@
+//! Conditional formatting rules for XLSX (used in both reading and writing).
+//!
+//! Rules are written as `<conditionalFormatting>` blocks in the worksheet XML.
+//! Rules that change the cell appearance (cell value comparisons, formulas,
+//! top N, above average) reference a differential format (`<dxf>`) in
+//! styles.xml, while visual rules (color scales, data bars, icon sets) carry
+//! their own parameters as conditional format value objects (`<cfvo>`).
+
+use std::fmt::Write as FmtWrite;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::common::RGBColor;
+use crate::common::xml::{attr, escape_xml};
+use crate::sheet::Result as SheetResult;
+
+use super::format::CellFormat;
+use super::worksheet::ConditionalFormatRule;
+
+/// Conditional formatting rule.
+#[derive(Debug, Clone)]
+pub struct ConditionalFormat {
+    /// Range (e.g., "A1:B10")
+    pub range: String,
+    /// Rule type
+    pub rule_type: ConditionalFormatType,
+    /// Priority (lower = higher priority)
+    pub priority: u32,
+    /// Format to apply (optional - can use built-in formats)
+    pub format: Option<CellFormat>,
+    /// Index of the differential format in styles.xml.
+    ///
+    /// Assigned when the workbook is saved for rules that have a `format`.
+    pub dxf_id: Option<u32>,
+}
+
+/// Conditional formatting rule types.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConditionalFormatType {
+    /// Cell value comparison (e.g., greater than, less than)
+    CellIs {
+        /// Operator (e.g., "greaterThan", "lessThan", "equal")
+        operator: String,
+        /// Formula to compare against
+        formula: String,
+    },
+    /// Color scale (2 or 3 color gradient)
+    ColorScale {
+        /// Lowest stop
+        min: ColorScaleStop,
+        /// Optional middle stop (makes this a 3-color scale)
+        mid: Option<ColorScaleStop>,
+        /// Highest stop
+        max: ColorScaleStop,
+    },
+    /// Data bar
+    DataBar {
+        /// Value mapped to the shortest bar
+        min: Cfvo,
+        /// Value mapped to the longest bar
+        max: Cfvo,
+        /// Bar color
+        color: RGBColor,
+        /// Show value alongside bar
+        show_value: bool,
+    },
+    /// Icon set
+    IconSet {
+        /// Icon set style
+        icon_set: IconSetStyle,
+        /// Threshold for each icon, lowest first. Must hold one entry per icon.
+        thresholds: Vec<Cfvo>,
+        /// Show values
+        show_value: bool,
+        /// Reverse the icon order
+        reverse: bool,
+    },
+    /// Top or bottom N values (or N percent)
+    Top10 {
+        /// Number of items (or percentage when `percent` is set)
+        rank: u32,
+        /// Interpret `rank` as a percentage
+        percent: bool,
+        /// Select the bottom values instead of the top ones
+        bottom: bool,
+    },
+    /// Values above or below the average of the range
+    AboveAverage {
+        /// Select values above (true) or below (false) the average
+        above: bool,
+        /// Include values equal to the average
+        equal_average: bool,
+        /// Number of standard deviations away from the average
+        std_dev: Option<u32>,
+    },
+    /// Formula-based
+    Expression {
+        /// Formula that returns TRUE/FALSE
+        formula: String,
+    },
+}
+
+impl ConditionalFormatType {
+    /// Create a 2-color scale from the lowest to the highest value.
+    pub fn two_color_scale(min_color: RGBColor, max_color: RGBColor) -> Self {
+        Self::ColorScale {
+            min: ColorScaleStop::new(Cfvo::min(), min_color),
+            mid: None,
+            max: ColorScaleStop::new(Cfvo::max(), max_color),
+        }
+    }
+
+    /// Create a 3-color scale with the middle color at the 50th percentile.
+    pub fn three_color_scale(
+        min_color: RGBColor,
+        mid_color: RGBColor,
+        max_color: RGBColor,
+    ) -> Self {
+        Self::ColorScale {
+            min: ColorScaleStop::new(Cfvo::min(), min_color),
+            mid: Some(ColorScaleStop::new(Cfvo::percentile(50.0), mid_color)),
+            max: ColorScaleStop::new(Cfvo::max(), max_color),
+        }
+    }
+
+    /// Create a data bar spanning the lowest to the highest value.
+    pub fn data_bar(color: RGBColor) -> Self {
+        Self::DataBar {
+            min: Cfvo::min(),
+            max: Cfvo::max(),
+            color,
+            show_value: true,
+        }
+    }
+
+    /// Create an icon set with thresholds evenly spread by percent.
+    pub fn icon_set(icon_set: IconSetStyle) -> Self {
+        let count = icon_set.icon_count();
+        let thresholds = (0..count)
+            .map(|i| Cfvo::percent((i * 100 / count) as f64))
+            .collect();
+        Self::IconSet {
+            icon_set,
+            thresholds,
+            show_value: true,
+            reverse: false,
+        }
+    }
+
+    /// The `type` attribute value of the `<cfRule>` element.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::CellIs { .. } => "cellIs",
+            Self::ColorScale { .. } => "colorScale",
+            Self::DataBar { .. } => "dataBar",
+            Self::IconSet { .. } => "iconSet",
+            Self::Top10 { .. } => "top10",
+            Self::AboveAverage { .. } => "aboveAverage",
+            Self::Expression { .. } => "expression",
+        }
+    }
+
+    /// Whether the rule applies a differential format when it matches.
+    pub fn uses_dxf(&self) -> bool {
+        !matches!(
+            self,
+            Self::ColorScale { .. } | Self::DataBar { .. } | Self::IconSet { .. }
+        )
+    }
+}
+
+/// A color scale stop: a threshold and the color used at that threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorScaleStop {
+    /// Threshold value
+    pub value: Cfvo,
+    /// Color at the threshold
+    pub color: RGBColor,
+}
+
+impl ColorScaleStop {
+    /// Create a new color scale stop.
+    pub fn new(value: Cfvo, color: RGBColor) -> Self {
+        Self { value, color }
+    }
+}
+
+/// Conditional format value object (`<cfvo>`), a threshold used by color
+/// scales, data bars and icon sets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cfvo {
+    /// How `value` is interpreted
+    pub value_type: CfvoType,
+    /// Threshold value or formula (unused for `Min`/`Max`)
+    pub value: Option<String>,
+    /// Whether the threshold is inclusive (icon sets only)
+    pub gte: bool,
+}
+
+impl Cfvo {
+    /// Create a threshold of the given type and value.
+    pub fn new(value_type: CfvoType, value: Option<String>) -> Self {
+        Self {
+            value_type,
+            value,
+            gte: true,
+        }
+    }
+
+    /// Lowest value in the range.
+    pub fn min() -> Self {
+        Self::new(CfvoType::Min, None)
+    }
+
+    /// Highest value in the range.
+    pub fn max() -> Self {
+        Self::new(CfvoType::Max, None)
+    }
+
+    /// A fixed number.
+    pub fn number(value: f64) -> Self {
+        Self::new(CfvoType::Num, Some(value.to_string()))
+    }
+
+    /// A percentage of the range between lowest and highest value.
+    pub fn percent(value: f64) -> Self {
+        Self::new(CfvoType::Percent, Some(value.to_string()))
+    }
+
+    /// A percentile of the values in the range.
+    pub fn percentile(value: f64) -> Self {
+        Self::new(CfvoType::Percentile, Some(value.to_string()))
+    }
+
+    /// The result of a formula.
+    pub fn formula(formula: &str) -> Self {
+        Self::new(CfvoType::Formula, Some(formula.to_string()))
+    }
+}
+
+/// Type of a conditional format value object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CfvoType {
+    Min,
+    Max,
+    Num,
+    Percent,
+    Percentile,
+    Formula,
+}
+
+impl CfvoType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Min => "min",
+            Self::Max => "max",
+            Self::Num => "num",
+            Self::Percent => "percent",
+            Self::Percentile => "percentile",
+            Self::Formula => "formula",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "num" => Some(Self::Num),
+            "percent" => Some(Self::Percent),
+            "percentile" => Some(Self::Percentile),
+            "formula" => Some(Self::Formula),
+            _ => None,
+        }
+    }
+}
+
+/// Built-in icon set styles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IconSetStyle {
+    Arrows3,
+    ArrowsGray3,
+    Flags3,
+    TrafficLights3,
+    TrafficLightsRimmed3,
+    Signs3,
+    Symbols3,
+    SymbolsUncircled3,
+    Arrows4,
+    ArrowsGray4,
+    RedToBlack4,
+    Rating4,
+    TrafficLights4,
+    Arrows5,
+    ArrowsGray5,
+    Rating5,
+    Quarters5,
+}
+
+impl IconSetStyle {
+    /// The name Excel uses for the icon set (e.g., "3TrafficLights1").
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Arrows3 => "3Arrows",
+            Self::ArrowsGray3 => "3ArrowsGray",
+            Self::Flags3 => "3Flags",
+            Self::TrafficLights3 => "3TrafficLights1",
+            Self::TrafficLightsRimmed3 => "3TrafficLights2",
+            Self::Signs3 => "3Signs",
+            Self::Symbols3 => "3Symbols",
+            Self::SymbolsUncircled3 => "3Symbols2",
+            Self::Arrows4 => "4Arrows",
+            Self::ArrowsGray4 => "4ArrowsGray",
+            Self::RedToBlack4 => "4RedToBlack",
+            Self::Rating4 => "4Rating",
+            Self::TrafficLights4 => "4TrafficLights",
+            Self::Arrows5 => "5Arrows",
+            Self::ArrowsGray5 => "5ArrowsGray",
+            Self::Rating5 => "5Rating",
+            Self::Quarters5 => "5Quarters",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "3Arrows" => Some(Self::Arrows3),
+            "3ArrowsGray" => Some(Self::ArrowsGray3),
+            "3Flags" => Some(Self::Flags3),
+            "3TrafficLights1" => Some(Self::TrafficLights3),
+            "3TrafficLights2" => Some(Self::TrafficLightsRimmed3),
+            "3Signs" => Some(Self::Signs3),
+            "3Symbols" => Some(Self::Symbols3),
+            "3Symbols2" => Some(Self::SymbolsUncircled3),
+            "4Arrows" => Some(Self::Arrows4),
+            "4ArrowsGray" => Some(Self::ArrowsGray4),
+            "4RedToBlack" => Some(Self::RedToBlack4),
+            "4Rating" => Some(Self::Rating4),
+            "4TrafficLights" => Some(Self::TrafficLights4),
+            "5Arrows" => Some(Self::Arrows5),
+            "5ArrowsGray" => Some(Self::ArrowsGray5),
+            "5Rating" => Some(Self::Rating5),
+            "5Quarters" => Some(Self::Quarters5),
+            _ => None,
+        }
+    }
+
+    /// Number of icons in the set.
+    pub fn icon_count(self) -> usize {
+        match self.as_str().as_bytes()[0] {
+            b'3' => 3,
+            b'4' => 4,
+            _ => 5,
+        }
+    }
+}
+
+/// Write the `<conditionalFormatting>` blocks for a worksheet.
+///
+/// Rules sharing a range are grouped into one block, in order of first
+/// appearance.
+pub fn write_conditional_formats(
+    xml: &mut String,
+    formats: &[ConditionalFormat],
+) -> SheetResult<()> {
+    let mut ranges: Vec<&str> = Vec::new();
+    for format in formats {
+        if !ranges.contains(&format.range.as_str()) {
+            ranges.push(&format.range);
+        }
+    }
+
+    for range in ranges {
+        write!(
+            xml,
+            r#"<conditionalFormatting sqref="{}">"#,
+            escape_xml(range)
+        )
+        .map_err(|e| format!("XML write error: {}", e))?;
+
+        for format in formats.iter().filter(|f| f.range == range) {
+            write_rule(xml, format)?;
+        }
+
+        xml.push_str("</conditionalFormatting>");
+    }
+
+    Ok(())
+}
+
+/// Write a single `<cfRule>` element.
+fn write_rule(xml: &mut String, format: &ConditionalFormat) -> SheetResult<()> {
+    let rule = &format.rule_type;
+    write!(xml, r#"<cfRule type="{}""#, rule.as_str())
+        .map_err(|e| format!("XML write error: {}", e))?;
+    if rule.uses_dxf()
+        && let Some(dxf_id) = format.dxf_id
+    {
+        write!(xml, r#" dxfId="{}""#, dxf_id).map_err(|e| format!("XML write error: {}", e))?;
+    }
+    write!(xml, r#" priority="{}""#, format.priority)
+        .map_err(|e| format!("XML write error: {}", e))?;
+
+    match rule {
+        ConditionalFormatType::CellIs { operator, formula } => {
+            write!(
+                xml,
+                r#" operator="{}"><formula>{}</formula>"#,
+                escape_xml(operator),
+                escape_xml(formula)
+            )
+            .map_err(|e| format!("XML write error: {}", e))?;
+        },
+        ConditionalFormatType::ColorScale { min, mid, max } => {
+            xml.push_str("><colorScale>");
+            // OOXML Spec: ALL cfvo elements must come BEFORE color elements
+            let stops: Vec<&ColorScaleStop> = std::iter::once(min)
+                .chain(mid.as_ref())
+                .chain(std::iter::once(max))
+                .collect();
+            for stop in &stops {
+                write_cfvo(xml, &stop.value, false)?;
+            }
+            for stop in &stops {
+                write_color(xml, stop.color)?;
+            }
+            xml.push_str("</colorScale>");
+        },
+        ConditionalFormatType::DataBar {
+            min,
+            max,
+            color,
+            show_value,
+        } => {
+            xml.push_str("><dataBar");
+            if !show_value {
+                xml.push_str(r#" showValue="0""#);
+            }
+            xml.push('>');
+            write_cfvo(xml, min, false)?;
+            write_cfvo(xml, max, false)?;
+            write_color(xml, *color)?;
+            xml.push_str("</dataBar>");
+        },
+        ConditionalFormatType::IconSet {
+            icon_set,
+            thresholds,
+            show_value,
+            reverse,
+        } => {
+            write!(xml, r#"><iconSet iconSet="{}""#, icon_set.as_str())
+                .map_err(|e| format!("XML write error: {}", e))?;
+            if !show_value {
+                xml.push_str(r#" showValue="0""#);
+            }
+            if *reverse {
+                xml.push_str(r#" reverse="1""#);
+            }
+            xml.push('>');
+            for threshold in thresholds {
+                write_cfvo(xml, threshold, true)?;
+            }
+            xml.push_str("</iconSet>");
+        },
+        ConditionalFormatType::Top10 {
+            rank,
+            percent,
+            bottom,
+        } => {
+            if *percent {
+                xml.push_str(r#" percent="1""#);
+            }
+            if *bottom {
+                xml.push_str(r#" bottom="1""#);
+            }
+            write!(xml, r#" rank="{}">"#, rank).map_err(|e| format!("XML write error: {}", e))?;
+        },
+        ConditionalFormatType::AboveAverage {
+            above,
+            equal_average,
+            std_dev,
+        } => {
+            if !above {
+                xml.push_str(r#" aboveAverage="0""#);
+            }
+            if *equal_average {
+                xml.push_str(r#" equalAverage="1""#);
+            }
+            if let Some(std_dev) = std_dev {
+                write!(xml, r#" stdDev="{}""#, std_dev)
+                    .map_err(|e| format!("XML write error: {}", e))?;
+            }
+            xml.push('>');
+        },
+        ConditionalFormatType::Expression { formula } => {
+            write!(xml, "><formula>{}</formula>", escape_xml(formula))
+                .map_err(|e| format!("XML write error: {}", e))?;
+        },
+    }
+
+    xml.push_str("</cfRule>");
+    Ok(())
+}
+
+fn write_cfvo(xml: &mut String, cfvo: &Cfvo, with_gte: bool) -> SheetResult<()> {
+    write!(xml, r#"<cfvo type="{}""#, cfvo.value_type.as_str())
+        .map_err(|e| format!("XML write error: {}", e))?;
+    if let Some(ref value) = cfvo.value {
+        write!(xml, r#" val="{}""#, escape_xml(value))
+            .map_err(|e| format!("XML write error: {}", e))?;
+    }
+    if with_gte && !cfvo.gte {
+        xml.push_str(r#" gte="0""#);
+    }
+    xml.push_str("/>");
+    Ok(())
+}
+
+fn write_color(xml: &mut String, color: RGBColor) -> SheetResult<()> {
+    write!(xml, r#"<color rgb="FF{}"/>"#, color.to_hex())
+        .map_err(|e| format!("XML write error: {}", e))?;
+    Ok(())
+}
+
+/// Parse every `<conditionalFormatting>` block in worksheet XML.
+pub(crate) fn parse_conditional_formats(content: &str) -> SheetResult<Vec<ConditionalFormatRule>> {
+    let mut reader = Reader::from_str(content);
+    let mut rules = Vec::new();
+
+    let mut range: Option<String> = None;
+    let mut current: Option<PendingRule> = None;
+    let mut in_formula = false;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Failed to parse conditional formatting: {}", e))?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                match e.local_name().as_ref() {
+                    b"conditionalFormatting" => range = attr(e, b"sqref"),
+                    b"cfRule" if range.is_some() => {
+                        let pending = PendingRule::from_start(e);
+                        if is_empty {
+                            rules.extend(pending.finish(range.as_deref()));
+                        } else {
+                            current = Some(pending);
+                        }
+                    },
+                    b"formula" if current.is_some() => in_formula = !is_empty,
+                    b"cfvo" => {
+                        if let Some(rule) = current.as_mut() {
+                            let value_type = attr(e, b"type")
+                                .and_then(|t| CfvoType::parse(&t))
+                                .unwrap_or(CfvoType::Num);
+                            rule.cfvos.push(Cfvo {
+                                value_type,
+                                value: attr(e, b"val"),
+                                gte: attr(e, b"gte").as_deref() != Some("0"),
+                            });
+                        }
+                    },
+                    b"color" => {
+                        if let Some(rule) = current.as_mut() {
+                            let color = attr(e, b"rgb")
+                                .and_then(|rgb| {
+                                    let hex = &rgb[rgb.len().saturating_sub(6)..];
+                                    RGBColor::from_hex(hex).ok()
+                                })
+                                .unwrap_or(RGBColor::BLACK);
+                            rule.colors.push(color);
+                        }
+                    },
+                    b"dataBar" | b"iconSet" => {
+                        if let Some(rule) = current.as_mut() {
+                            rule.show_value = attr(e, b"showValue").as_deref() != Some("0");
+                            rule.reverse = attr(e, b"reverse").as_deref() == Some("1");
+                            rule.icon_set = attr(e, b"iconSet");
+                        }
+                    },
+                    _ => {},
+                }
+            },
+            Event::Text(ref e) if in_formula => {
+                if let Some(rule) = current.as_mut() {
+                    let text = e
+                        .decode()
+                        .map_err(|e| format!("Failed to decode formula: {}", e))?;
+                    rule.formula_text(&text);
+                }
+            },
+            Event::GeneralRef(ref e) if in_formula => {
+                if let Some(rule) = current.as_mut() {
+                    let name = e
+                        .decode()
+                        .map_err(|e| format!("Failed to decode formula: {}", e))?;
+                    let resolved =
+                        quick_xml::escape::resolve_predefined_entity(&name).unwrap_or_default();
+                    rule.formula_text(resolved);
+                }
+            },
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"formula" => {
+                    in_formula = false;
+                    if let Some(rule) = current.as_mut() {
+                        rule.formula_done();
+                    }
+                },
+                b"cfRule" => {
+                    if let Some(pending) = current.take() {
+                        rules.extend(pending.finish(range.as_deref()));
+                    }
+                },
+                b"conditionalFormatting" => range = None,
+                _ => {},
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok(rules)
+}
+
+/// A `<cfRule>` whose children are still being read.
+struct PendingRule {
+    rule_type: String,
+    priority: u32,
+    dxf_id: Option<u32>,
+    operator: Option<String>,
+    rank: Option<u32>,
+    percent: bool,
+    bottom: bool,
+    above_average: bool,
+    equal_average: bool,
+    std_dev: Option<u32>,
+    formulas: Vec<String>,
+    formula: Option<String>,
+    cfvos: Vec<Cfvo>,
+    colors: Vec<RGBColor>,
+    icon_set: Option<String>,
+    show_value: bool,
+    reverse: bool,
+}
+
+impl PendingRule {
+    fn from_start(e: &BytesStart<'_>) -> Self {
+        let flag = |name: &[u8]| attr(e, name).is_some_and(|v| v == "1" || v == "true");
+        Self {
+            rule_type: attr(e, b"type").unwrap_or_default(),
+            priority: attr(e, b"priority")
+                .and_then(|p| p.parse().ok())
+                .unwrap_or(0),
+            dxf_id: attr(e, b"dxfId").and_then(|d| d.parse().ok()),
+            operator: attr(e, b"operator"),
+            rank: attr(e, b"rank").and_then(|r| r.parse().ok()),
+            percent: flag(b"percent"),
+            bottom: flag(b"bottom"),
+            above_average: attr(e, b"aboveAverage").is_none_or(|v| v != "0" && v != "false"),
+            equal_average: flag(b"equalAverage"),
+            std_dev: attr(e, b"stdDev").and_then(|s| s.parse().ok()),
+            formulas: Vec::new(),
+            formula: None,
+            cfvos: Vec::new(),
+            colors: Vec::new(),
+            icon_set: None,
+            show_value: true,
+            reverse: false,
+        }
+    }
+
+    fn formula_text(&mut self, text: &str) {
+        self.formula.get_or_insert_with(String::new).push_str(text);
+    }
+
+    fn formula_done(&mut self) {
+        self.formulas.push(self.formula.take().unwrap_or_default());
+    }
+
+    fn finish(self, range: Option<&str>) -> Option<ConditionalFormatRule> {
+        let range = range?.to_string();
+        let rule = self.to_rule_type();
+        Some(ConditionalFormatRule {
+            range,
+            rule_type: self.rule_type,
+            priority: self.priority,
+            dxf_id: self.dxf_id,
+            formulas: self.formulas,
+            rule,
+        })
+    }
+
+    fn to_rule_type(&self) -> Option<ConditionalFormatType> {
+        let first_formula = || self.formulas.first().cloned().unwrap_or_default();
+        match self.rule_type.as_str() {
+            "cellIs" => Some(ConditionalFormatType::CellIs {
+                operator: self.operator.clone()?,
+                formula: first_formula(),
+            }),
+            "expression" => Some(ConditionalFormatType::Expression {
+                formula: first_formula(),
+            }),
+            "colorScale" => {
+                let stops: Vec<ColorScaleStop> = self
+                    .cfvos
+                    .iter()
+                    .zip(&self.colors)
+                    .map(|(cfvo, color)| ColorScaleStop::new(cfvo.clone(), *color))
+                    .collect();
+                match stops.as_slice() {
+                    [min, max] => Some(ConditionalFormatType::ColorScale {
+                        min: min.clone(),
+                        mid: None,
+                        max: max.clone(),
+                    }),
+                    [min, mid, max] => Some(ConditionalFormatType::ColorScale {
+                        min: min.clone(),
+                        mid: Some(mid.clone()),
+                        max: max.clone(),
+                    }),
+                    _ => None,
+                }
+            },
+            "dataBar" => Some(ConditionalFormatType::DataBar {
+                min: self.cfvos.first()?.clone(),
+                max: self.cfvos.get(1)?.clone(),
+                color: *self.colors.first()?,
+                show_value: self.show_value,
+            }),
+            "iconSet" => Some(ConditionalFormatType::IconSet {
+                // Excel omits the attribute for its default set
+                icon_set: self
+                    .icon_set
+                    .as_deref()
+                    .map_or(Some(IconSetStyle::TrafficLights3), IconSetStyle::parse)?,
+                thresholds: self.cfvos.clone(),
+                show_value: self.show_value,
+                reverse: self.reverse,
+            }),
+            "top10" => Some(ConditionalFormatType::Top10 {
+                rank: self.rank?,
+                percent: self.percent,
+                bottom: self.bottom,
+            }),
+            "aboveAverage" => Some(ConditionalFormatType::AboveAverage {
+                above: self.above_average,
+                equal_average: self.equal_average,
+                std_dev: self.std_dev,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(range: &str, rule_type: ConditionalFormatType, priority: u32) -> ConditionalFormat {
+        ConditionalFormat {
+            range: range.to_string(),
+            rule_type,
+            priority,
+            format: None,
+            dxf_id: None,
+        }
+    }
+
+    #[test]
+    fn writes_and_parses_visual_rules() {
+        let formats = vec![
+            rule(
+                "A1:A10",
+                ConditionalFormatType::three_color_scale(
+                    RGBColor::RED,
+                    RGBColor::YELLOW,
+                    RGBColor::LIME,
+                ),
+                1,
+            ),
+            rule(
+                "B1:B10",
+                ConditionalFormatType::DataBar {
+                    min: Cfvo::number(0.0),
+                    max: Cfvo::percentile(90.0),
+                    color: RGBColor::new(0x63, 0x8E, 0xC6),
+                    show_value: false,
+                },
+                2,
+            ),
+            rule(
+                "A1:A10",
+                ConditionalFormatType::IconSet {
+                    icon_set: IconSetStyle::Arrows4,
+                    thresholds: vec![
+                        Cfvo::percent(0.0),
+                        Cfvo::percent(25.0),
+                        Cfvo {
+                            gte: false,
+                            ..Cfvo::percent(50.0)
+                        },
+                        Cfvo::formula("$D$1"),
+                    ],
+                    show_value: true,
+                    reverse: true,
+                },
+                3,
+            ),
+        ];
+
+        let mut xml = String::from("<worksheet>");
+        write_conditional_formats(&mut xml, &formats).unwrap();
+        xml.push_str("</worksheet>");
+
+        // Rules on the same range share a block
+        assert_eq!(xml.matches("<conditionalFormatting ").count(), 2);
+
+        let rules = parse_conditional_formats(&xml).unwrap();
+        assert_eq!(rules.len(), 3);
+        for format in &formats {
+            let parsed = rules
+                .iter()
+                .find(|r| r.priority == format.priority)
+                .unwrap();
+            assert_eq!(parsed.range, format.range);
+            assert_eq!(parsed.rule.as_ref(), Some(&format.rule_type));
+        }
+    }
+
+    #[test]
+    fn writes_and_parses_dxf_rules() {
+        let mut top = rule(
+            "C1:C20",
+            ConditionalFormatType::Top10 {
+                rank: 10,
+                percent: true,
+                bottom: true,
+            },
+            1,
+        );
+        top.dxf_id = Some(0);
+        let mut average = rule(
+            "C1:C20",
+            ConditionalFormatType::AboveAverage {
+                above: false,
+                equal_average: true,
+                std_dev: Some(2),
+            },
+            2,
+        );
+        average.dxf_id = Some(1);
+        let cell_is = rule(
+            "C1:C20",
+            ConditionalFormatType::CellIs {
+                operator: "between".to_string(),
+                formula: "\"a<b\"".to_string(),
+            },
+            3,
+        );
+
+        let formats = vec![top, average, cell_is];
+        let mut xml = String::new();
+        write_conditional_formats(&mut xml, &formats).unwrap();
+        assert!(xml.contains(
+            r#"<cfRule type="top10" dxfId="0" priority="1" percent="1" bottom="1" rank="10">"#
+        ));
+
+        let rules = parse_conditional_formats(&xml).unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].dxf_id, Some(0));
+        assert_eq!(rules[1].dxf_id, Some(1));
+        assert_eq!(rules[2].dxf_id, None);
+        for (parsed, format) in rules.iter().zip(&formats) {
+            assert_eq!(parsed.rule.as_ref(), Some(&format.rule_type));
+        }
+    }
+
+    #[test]
+    fn default_icon_set_thresholds() {
+        match ConditionalFormatType::icon_set(IconSetStyle::Rating5) {
+            ConditionalFormatType::IconSet { thresholds, .. } => {
+                let values: Vec<_> = thresholds.iter().map(|t| t.value.clone()).collect();
+                assert_eq!(
+                    values,
+                    ["0", "20", "40", "60", "80"].map(|v| Some(v.to_string()))
+                );
+            },
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,741 @@
+//! Data validation rules for XLSX (used in both reading and writing).
+//!
+//! Rules are stored in the worksheet's `<dataValidations>` block. Excel 2010
+//! and later write list rules whose source lives on another sheet to an
+//! `<x14:dataValidations>` block in the worksheet's `extLst` instead; both
+//! are read into the same [`DataValidationRule`].
+
+use std::fmt::Write as FmtWrite;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::common::xml::attr;
+use crate::common::xml::escape_xml;
+use crate::sheet::Result as SheetResult;
+
+/// Data validation rule.
+///
+/// The same struct is returned by
+/// [`Worksheet::get_data_validations`](super::Worksheet::get_data_validations)
+/// and accepted by
+/// [`MutableWorksheet::add_data_validation`](super::writer::MutableWorksheet::add_data_validation).
+///
+/// # Examples
+///
+/// ```
+/// use litchi::ooxml::xlsx::{DataValidationRule, DataValidationType};
+///
+/// let rule = DataValidationRule::new(
+///     "B2:B100 D2:D100",
+///     DataValidationType::List {
+///         values: vec!["Yes".to_string(), "No".to_string()],
+///     },
+/// )
+/// .with_error_message("Invalid answer", "Pick Yes or No.");
+/// assert_eq!(rule.ranges().collect::<Vec<_>>(), ["B2:B100", "D2:D100"]);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct DataValidationRule {
+    /// Space-separated ranges the rule applies to (e.g., "A1:A10 C1:C10")
+    pub range: String,
+    /// What values are allowed
+    pub validation_type: DataValidationType,
+    /// Whether empty cells pass validation
+    pub allow_blank: bool,
+    /// Hide the in-cell drop-down arrow of list rules.
+    ///
+    /// This is the `showDropDown` attribute, which despite its name turns
+    /// the arrow off when set.
+    pub hide_drop_down: bool,
+    /// Show the input message when the cell is selected
+    pub show_input_message: bool,
+    /// Title of the input message
+    pub input_title: Option<String>,
+    /// Text of the input message
+    pub input_message: Option<String>,
+    /// Show the error alert when an invalid value is entered
+    pub show_error_alert: bool,
+    /// Title of the error alert
+    pub error_title: Option<String>,
+    /// Text of the error alert
+    pub error_message: Option<String>,
+    /// Whether the error alert rejects the value or only warns
+    pub error_style: DataValidationErrorStyle,
+}
+
+impl DataValidationRule {
+    /// Create a rule with Excel's defaults for new validations: blanks are
+    /// allowed and both the input message and error alert are enabled.
+    pub fn new(range: &str, validation_type: DataValidationType) -> Self {
+        Self {
+            range: range.to_string(),
+            validation_type,
+            allow_blank: true,
+            hide_drop_down: false,
+            show_input_message: true,
+            input_title: None,
+            input_message: None,
+            show_error_alert: true,
+            error_title: None,
+            error_message: None,
+            error_style: DataValidationErrorStyle::Stop,
+        }
+    }
+
+    /// Set the input message shown when a cell in the range is selected.
+    pub fn with_input_message(mut self, title: &str, message: &str) -> Self {
+        self.show_input_message = true;
+        self.input_title = Some(title.to_string());
+        self.input_message = Some(message.to_string());
+        self
+    }
+
+    /// Set the error alert shown when an invalid value is entered.
+    pub fn with_error_message(mut self, title: &str, message: &str) -> Self {
+        self.show_error_alert = true;
+        self.error_title = Some(title.to_string());
+        self.error_message = Some(message.to_string());
+        self
+    }
+
+    /// Iterate over the individual ranges of a multi-range rule.
+    pub fn ranges(&self) -> impl Iterator<Item = &str> {
+        self.range.split_whitespace()
+    }
+}
+
+/// Data validation types.
+///
+/// Bounds are formulas as stored in the file, without a leading `=`: either
+/// constants (`"10"`, `"0.5"`, a date serial such as `"45292"`) or
+/// expressions (`"$B$1"`, `"DATE(2024,1,1)"`).
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataValidationType {
+    /// Any value (used to attach an input message without restricting input)
+    Any,
+    /// Whole numbers
+    Whole {
+        operator: DataValidationOperator,
+        value1: String,
+        value2: Option<String>,
+    },
+    /// Decimal numbers
+    Decimal {
+        operator: DataValidationOperator,
+        value1: String,
+        value2: Option<String>,
+    },
+    /// One of a fixed list of values.
+    ///
+    /// Excel stores the list as one comma-separated string of at most 255
+    /// characters, so values cannot contain commas.
+    List { values: Vec<String> },
+    /// One of the values in a range or named range (e.g., "$A$1:$A$5",
+    /// "Lists!$A:$A")
+    ListRange { source: String },
+    /// Dates
+    Date {
+        operator: DataValidationOperator,
+        value1: String,
+        value2: Option<String>,
+    },
+    /// Times of day
+    Time {
+        operator: DataValidationOperator,
+        value1: String,
+        value2: Option<String>,
+    },
+    /// Text whose length satisfies the bounds
+    TextLength {
+        operator: DataValidationOperator,
+        value1: String,
+        value2: Option<String>,
+    },
+    /// Values for which the formula evaluates to true
+    Custom { formula: String },
+}
+
+impl DataValidationType {
+    /// The `type` attribute value, or `None` for [`DataValidationType::Any`].
+    fn as_str(&self) -> Option<&'static str> {
+        match self {
+            Self::Any => None,
+            Self::Whole { .. } => Some("whole"),
+            Self::Decimal { .. } => Some("decimal"),
+            Self::List { .. } | Self::ListRange { .. } => Some("list"),
+            Self::Date { .. } => Some("date"),
+            Self::Time { .. } => Some("time"),
+            Self::TextLength { .. } => Some("textLength"),
+            Self::Custom { .. } => Some("custom"),
+        }
+    }
+
+    /// Operator and bounds of comparison types.
+    fn bounds(&self) -> Option<(DataValidationOperator, &str, Option<&str>)> {
+        match self {
+            Self::Whole {
+                operator,
+                value1,
+                value2,
+            }
+            | Self::Decimal {
+                operator,
+                value1,
+                value2,
+            }
+            | Self::Date {
+                operator,
+                value1,
+                value2,
+            }
+            | Self::Time {
+                operator,
+                value1,
+                value2,
+            }
+            | Self::TextLength {
+                operator,
+                value1,
+                value2,
+            } => Some((*operator, value1, value2.as_deref())),
+            _ => None,
+        }
+    }
+
+    /// Build a type from the attributes and formulas of a parsed rule.
+    fn from_parts(
+        type_name: Option<&str>,
+        operator: DataValidationOperator,
+        formula1: Option<String>,
+        formula2: Option<String>,
+    ) -> Option<Self> {
+        let value1 = formula1.unwrap_or_default();
+        let validation_type = match type_name.unwrap_or("none") {
+            "none" => Self::Any,
+            "whole" => Self::Whole {
+                operator,
+                value1,
+                value2: formula2,
+            },
+            "decimal" => Self::Decimal {
+                operator,
+                value1,
+                value2: formula2,
+            },
+            "date" => Self::Date {
+                operator,
+                value1,
+                value2: formula2,
+            },
+            "time" => Self::Time {
+                operator,
+                value1,
+                value2: formula2,
+            },
+            "textLength" => Self::TextLength {
+                operator,
+                value1,
+                value2: formula2,
+            },
+            "list" => match value1.strip_prefix('"').and_then(|s| s.strip_suffix('"')) {
+                Some(inline) => Self::List {
+                    values: inline
+                        .split(',')
+                        .map(|value| value.replace("\"\"", "\""))
+                        .collect(),
+                },
+                None => Self::ListRange { source: value1 },
+            },
+            "custom" => Self::Custom { formula: value1 },
+            _ => return None,
+        };
+        Some(validation_type)
+    }
+}
+
+/// Data validation operators.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DataValidationOperator {
+    #[default]
+    Between,
+    NotBetween,
+    Equal,
+    NotEqual,
+    GreaterThan,
+    LessThan,
+    GreaterThanOrEqual,
+    LessThanOrEqual,
+}
+
+impl DataValidationOperator {
+    pub(crate) fn as_str(&self) -> &'static str {
+        match self {
+            Self::Between => "between",
+            Self::NotBetween => "notBetween",
+            Self::Equal => "equal",
+            Self::NotEqual => "notEqual",
+            Self::GreaterThan => "greaterThan",
+            Self::LessThan => "lessThan",
+            Self::GreaterThanOrEqual => "greaterThanOrEqual",
+            Self::LessThanOrEqual => "lessThanOrEqual",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "between" => Self::Between,
+            "notBetween" => Self::NotBetween,
+            "equal" => Self::Equal,
+            "notEqual" => Self::NotEqual,
+            "greaterThan" => Self::GreaterThan,
+            "lessThan" => Self::LessThan,
+            "greaterThanOrEqual" => Self::GreaterThanOrEqual,
+            "lessThanOrEqual" => Self::LessThanOrEqual,
+            _ => return None,
+        })
+    }
+}
+
+/// How an invalid value is handled by the error alert.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DataValidationErrorStyle {
+    /// Reject the value
+    #[default]
+    Stop,
+    /// Warn, but let the user keep the value
+    Warning,
+    /// Inform the user and keep the value
+    Information,
+}
+
+impl DataValidationErrorStyle {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stop => "stop",
+            Self::Warning => "warning",
+            Self::Information => "information",
+        }
+    }
+
+    fn parse(s: &str) -> Self {
+        match s {
+            "warning" => Self::Warning,
+            "information" => Self::Information,
+            _ => Self::Stop,
+        }
+    }
+}
+
+/// Write the `<dataValidations>` block for a worksheet.
+pub fn write_data_validations(
+    xml: &mut String,
+    validations: &[DataValidationRule],
+) -> SheetResult<()> {
+    if validations.is_empty() {
+        return Ok(());
+    }
+
+    write!(xml, r#"<dataValidations count="{}">"#, validations.len())
+        .map_err(|e| format!("XML write error: {}", e))?;
+    for validation in validations {
+        write_rule(xml, validation)?;
+    }
+    xml.push_str("</dataValidations>");
+    Ok(())
+}
+
+/// Write a single `<dataValidation>` element, attributes in schema order.
+fn write_rule(xml: &mut String, validation: &DataValidationRule) -> SheetResult<()> {
+    let validation_type = &validation.validation_type;
+    let bounds = validation_type.bounds();
+
+    xml.push_str("<dataValidation");
+    if let Some(type_name) = validation_type.as_str() {
+        write!(xml, r#" type="{}""#, type_name).map_err(|e| format!("XML write error: {}", e))?;
+    }
+    if validation.error_style != DataValidationErrorStyle::Stop {
+        write!(xml, r#" errorStyle="{}""#, validation.error_style.as_str())
+            .map_err(|e| format!("XML write error: {}", e))?;
+    }
+    if let Some((operator, _, _)) = bounds
+        && operator != DataValidationOperator::Between
+    {
+        write!(xml, r#" operator="{}""#, operator.as_str())
+            .map_err(|e| format!("XML write error: {}", e))?;
+    }
+    if validation.allow_blank {
+        xml.push_str(r#" allowBlank="1""#);
+    }
+    if validation.hide_drop_down {
+        xml.push_str(r#" showDropDown="1""#);
+    }
+    if validation.show_input_message {
+        xml.push_str(r#" showInputMessage="1""#);
+    }
+    if validation.show_error_alert {
+        xml.push_str(r#" showErrorMessage="1""#);
+    }
+    for (name, value) in [
+        ("errorTitle", &validation.error_title),
+        ("error", &validation.error_message),
+        ("promptTitle", &validation.input_title),
+        ("prompt", &validation.input_message),
+    ] {
+        if let Some(value) = value {
+            write!(xml, r#" {}="{}""#, name, escape_xml(value))
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+    }
+    write!(xml, r#" sqref="{}""#, escape_xml(&validation.range))
+        .map_err(|e| format!("XML write error: {}", e))?;
+
+    let (formula1, formula2) = match validation_type {
+        DataValidationType::Any => (None, None),
+        DataValidationType::List { values } => {
+            let values: Vec<String> = values.iter().map(|v| v.replace('"', "\"\"")).collect();
+            (Some(format!("\"{}\"", values.join(","))), None)
+        },
+        DataValidationType::ListRange { source } => (Some(source.clone()), None),
+        DataValidationType::Custom { formula } => (Some(formula.clone()), None),
+        _ => match bounds {
+            Some((_, value1, value2)) => (Some(value1.to_string()), value2.map(str::to_string)),
+            None => (None, None),
+        },
+    };
+
+    if formula1.is_none() {
+        xml.push_str("/>");
+        return Ok(());
+    }
+
+    xml.push('>');
+    for (name, formula) in [("formula1", formula1), ("formula2", formula2)] {
+        if let Some(formula) = formula {
+            let formula = formula.strip_prefix('=').unwrap_or(&formula);
+            write!(xml, "<{name}>{}</{name}>", escape_xml(formula))
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+    }
+    xml.push_str("</dataValidation>");
+    Ok(())
+}
+
+/// Parse `<dataValidations>` and `<x14:dataValidations>` blocks.
+///
+/// Rules with an unknown type are skipped.
+pub(crate) fn parse_data_validations(content: &str) -> SheetResult<Vec<DataValidationRule>> {
+    let mut reader = Reader::from_str(content);
+    let mut rules = Vec::new();
+
+    let mut current: Option<PendingRule> = None;
+    let mut text_target: Option<TextTarget> = None;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Failed to parse data validations: {}", e))?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                match e.local_name().as_ref() {
+                    b"dataValidation" => {
+                        let pending = PendingRule::from_start(e);
+                        if is_empty {
+                            rules.extend(pending.finish());
+                        } else {
+                            current = Some(pending);
+                        }
+                    },
+                    b"formula1" if current.is_some() && !is_empty => {
+                        text_target = Some(TextTarget::Formula1);
+                    },
+                    b"formula2" if current.is_some() && !is_empty => {
+                        text_target = Some(TextTarget::Formula2);
+                    },
+                    b"sqref" if current.is_some() && !is_empty => {
+                        text_target = Some(TextTarget::Sqref);
+                    },
+                    _ => {},
+                }
+            },
+            Event::Text(ref e) => {
+                if let (Some(rule), Some(target)) = (current.as_mut(), text_target) {
+                    let text = e
+                        .decode()
+                        .map_err(|e| format!("Failed to decode data validation: {}", e))?;
+                    rule.push_text(target, &text);
+                }
+            },
+            Event::GeneralRef(ref e) => {
+                if let (Some(rule), Some(target)) = (current.as_mut(), text_target) {
+                    let name = e
+                        .decode()
+                        .map_err(|e| format!("Failed to decode data validation: {}", e))?;
+                    let resolved =
+                        quick_xml::escape::resolve_predefined_entity(&name).unwrap_or_default();
+                    rule.push_text(target, resolved);
+                }
+            },
+            Event::End(ref e) => match e.local_name().as_ref() {
+                b"formula1" | b"formula2" | b"sqref" => text_target = None,
+                b"dataValidation" => {
+                    if let Some(pending) = current.take() {
+                        rules.extend(pending.finish());
+                    }
+                },
+                _ => {},
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok(rules)
+}
+
+/// Element whose text is being collected.
+#[derive(Debug, Clone, Copy)]
+enum TextTarget {
+    Formula1,
+    Formula2,
+    Sqref,
+}
+
+/// A `<dataValidation>` whose child elements are still being read.
+struct PendingRule {
+    type_name: Option<String>,
+    operator: DataValidationOperator,
+    rule: DataValidationRule,
+    formula1: Option<String>,
+    formula2: Option<String>,
+}
+
+impl PendingRule {
+    fn from_start(e: &BytesStart<'_>) -> Self {
+        let flag = |name: &[u8]| matches!(attr(e, name).as_deref(), Some("1" | "true"));
+        Self {
+            type_name: attr(e, b"type"),
+            operator: attr(e, b"operator")
+                .and_then(|op| DataValidationOperator::parse(&op))
+                .unwrap_or_default(),
+            rule: DataValidationRule {
+                range: attr(e, b"sqref").unwrap_or_default(),
+                validation_type: DataValidationType::Any,
+                allow_blank: flag(b"allowBlank"),
+                hide_drop_down: flag(b"showDropDown"),
+                show_input_message: flag(b"showInputMessage"),
+                input_title: attr(e, b"promptTitle"),
+                input_message: attr(e, b"prompt"),
+                show_error_alert: flag(b"showErrorMessage"),
+                error_title: attr(e, b"errorTitle"),
+                error_message: attr(e, b"error"),
+                error_style: attr(e, b"errorStyle")
+                    .map(|s| DataValidationErrorStyle::parse(&s))
+                    .unwrap_or_default(),
+            },
+            formula1: None,
+            formula2: None,
+        }
+    }
+
+    fn push_text(&mut self, target: TextTarget, text: &str) {
+        let buffer = match target {
+            TextTarget::Formula1 => self.formula1.get_or_insert_with(String::new),
+            TextTarget::Formula2 => self.formula2.get_or_insert_with(String::new),
+            TextTarget::Sqref => &mut self.rule.range,
+        };
+        buffer.push_str(text);
+    }
+
+    fn finish(self) -> Option<DataValidationRule> {
+        // Pretty-printed files put whitespace around <xm:f> and <xm:sqref>
+        let range = self.rule.range.trim().to_string();
+        if range.is_empty() {
+            return None;
+        }
+        let validation_type = DataValidationType::from_parts(
+            self.type_name.as_deref(),
+            self.operator,
+            self.formula1.map(|f| f.trim().to_string()),
+            self.formula2.map(|f| f.trim().to_string()),
+        )?;
+        Some(DataValidationRule {
+            range,
+            validation_type,
+            ..self.rule
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn between(value1: &str, value2: &str) -> (DataValidationOperator, String, Option<String>) {
+        (
+            DataValidationOperator::Between,
+            value1.to_string(),
+            Some(value2.to_string()),
+        )
+    }
+
+    #[test]
+    fn writes_and_parses_every_type() {
+        let (operator, value1, value2) = between("1", "10");
+        let mut time = DataValidationRule::new(
+            "E1",
+            DataValidationType::Time {
+                operator: DataValidationOperator::GreaterThanOrEqual,
+                value1: "0.375".to_string(),
+                value2: None,
+            },
+        );
+        time.error_style = DataValidationErrorStyle::Warning;
+        let mut drop_down = DataValidationRule::new(
+            "B1:B5 D1:D5",
+            DataValidationType::List {
+                values: vec![
+                    "Red".to_string(),
+                    "Say \"hi\"".to_string(),
+                    "A&B".to_string(),
+                ],
+            },
+        )
+        .with_input_message("Colour", "Choose a colour")
+        .with_error_message("Oops", "Not in <list>");
+        drop_down.hide_drop_down = true;
+        let mut any = DataValidationRule::new("H1", DataValidationType::Any)
+            .with_input_message("Note", "Free text");
+        any.allow_blank = false;
+        any.show_error_alert = false;
+
+        let rules = vec![
+            DataValidationRule::new(
+                "A1:A10",
+                DataValidationType::Whole {
+                    operator,
+                    value1,
+                    value2,
+                },
+            ),
+            DataValidationRule::new(
+                "C1",
+                DataValidationType::Decimal {
+                    operator: DataValidationOperator::LessThan,
+                    value1: "$Z$1".to_string(),
+                    value2: None,
+                },
+            ),
+            drop_down,
+            DataValidationRule::new(
+                "C2",
+                DataValidationType::ListRange {
+                    source: "Lists!$A$1:$A$5".to_string(),
+                },
+            ),
+            DataValidationRule::new(
+                "D1",
+                DataValidationType::Date {
+                    operator: DataValidationOperator::NotBetween,
+                    value1: "DATE(2024,1,1)".to_string(),
+                    value2: Some("45657".to_string()),
+                },
+            ),
+            time,
+            DataValidationRule::new(
+                "F1",
+                DataValidationType::TextLength {
+                    operator: DataValidationOperator::LessThanOrEqual,
+                    value1: "20".to_string(),
+                    value2: None,
+                },
+            ),
+            DataValidationRule::new(
+                "G1",
+                DataValidationType::Custom {
+                    formula: "AND(G1>0,G1<>\"x\")".to_string(),
+                },
+            ),
+            any,
+        ];
+
+        let mut xml = String::from("<worksheet>");
+        write_data_validations(&mut xml, &rules).unwrap();
+        xml.push_str("</worksheet>");
+
+        assert!(xml.contains(r#"<dataValidations count="9">"#));
+        assert!(xml.contains("Say &quot;&quot;hi&quot;&quot;,A&amp;B"));
+        assert!(xml.contains(r#"sqref="H1"/>"#));
+
+        let parsed = parse_data_validations(&xml).unwrap();
+        assert_eq!(parsed, rules);
+    }
+
+    #[test]
+    fn parses_excel_output() {
+        let xml = r#"<worksheet>
+            <dataValidations count="2">
+                <dataValidation type="whole" operator="greaterThan" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="A2:A20">
+                    <formula1>0</formula1>
+                </dataValidation>
+                <dataValidation type="list" allowBlank="1" showInputMessage="1" showErrorMessage="1" sqref="B2:B20">
+                    <formula1>$H$1:$H$3</formula1>
+                </dataValidation>
+            </dataValidations>
+            <extLst><ext uri="{CCE6A557-97BC-4b89-ADB6-D9C93CAAB3DF}" xmlns:x14="http://schemas.microsoft.com/office/spreadsheetml/2009/9/main">
+                <x14:dataValidations count="1" xmlns:xm="http://schemas.microsoft.com/office/excel/2006/main">
+                    <x14:dataValidation type="list" allowBlank="1" showInputMessage="1" showErrorMessage="1">
+                        <x14:formula1><xm:f>Lists!$A$1:$A$4</xm:f></x14:formula1>
+                        <xm:sqref>C2:C20 E2</xm:sqref>
+                    </x14:dataValidation>
+                </x14:dataValidations>
+            </ext></extLst>
+        </worksheet>"#;
+
+        let rules = parse_data_validations(xml).unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(
+            rules[0].validation_type,
+            DataValidationType::Whole {
+                operator: DataValidationOperator::GreaterThan,
+                value1: "0".to_string(),
+                value2: None,
+            }
+        );
+        assert_eq!(
+            rules[1].validation_type,
+            DataValidationType::ListRange {
+                source: "$H$1:$H$3".to_string(),
+            }
+        );
+        assert_eq!(rules[2].range, "C2:C20 E2");
+        assert_eq!(rules[2].ranges().count(), 2);
+        assert_eq!(
+            rules[2].validation_type,
+            DataValidationType::ListRange {
+                source: "Lists!$A$1:$A$4".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn operator_names_round_trip() {
+        for operator in [
+            DataValidationOperator::Between,
+            DataValidationOperator::NotBetween,
+            DataValidationOperator::Equal,
+            DataValidationOperator::NotEqual,
+            DataValidationOperator::GreaterThan,
+            DataValidationOperator::LessThan,
+            DataValidationOperator::GreaterThanOrEqual,
+            DataValidationOperator::LessThanOrEqual,
+        ] {
+            assert_eq!(
+                DataValidationOperator::parse(operator.as_str()),
+                Some(operator)
+            );
+        }
+        assert_eq!(DataValidationOperator::parse("between?"), None);
+    }
+}
@@ -0,0 +1,1982 @@
+//! In-place editing of existing Excel workbooks.
+//!
+//! [`WorkbookEditor`] changes individual cells of an existing `.xlsx` file
+//! without regenerating the package. Only the worksheets that were edited,
+//! the shared string table and the workbook part are rewritten, and the
+//! calculation chain is dropped so Excel rebuilds it. Every other member of
+//! the archive, including pivot tables, charts and parts this crate does not
+//! understand, is copied byte-for-byte.
+//!
+//! Rows and columns can be inserted and deleted through
+//! [`EditableWorksheet`]; the parts referring to the moved cells are
+//! rewritten as well.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use litchi::ooxml::xlsx::WorkbookEditor;
+//!
+//! let mut editor = WorkbookEditor::open("report.xlsx")?;
+//! editor.set_cell("Summary", 2, 3, 42.5)?;
+//! editor.set_cell("Summary", 3, 3, "revised")?;
+//! editor.save("report-revised.xlsx")?;
+//! # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+//! ```
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::path::Path;
+
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use crate::common::xml::{attr, escape_xml, unescape_xml};
+use crate::ooxml::opc::constants::content_type as ct;
+use crate::ooxml::opc::constants::relationship_type as rt;
+use crate::ooxml::opc::phys_pkg::{PhysPkgEditor, PhysPkgReader};
+use crate::ooxml::opc::{OpcPackage, PackURI};
+use crate::sheet::date_system::is_date_format_id;
+use crate::sheet::{CellValue, Result as SheetResult};
+
+use super::cell::Cell;
+use super::parsers::workbook_parser;
+
+mod structure;
+
+use super::references::{Axis, MAX_COL, MAX_ROW, Shift, shift_range};
+use structure::Fixup;
+
+/// Children of `<workbook>` that follow `<calcPr>`, in schema order.
+const AFTER_CALC_PR: &[&[u8]] = &[
+    b"oleSize",
+    b"customWorkbookViews",
+    b"pivotCaches",
+    b"smartTagPr",
+    b"smartTagTypes",
+    b"webPublishing",
+    b"fileRecoveryPr",
+    b"webPublishObjects",
+    b"extLst",
+];
+
+/// A sheet listed in the workbook part.
+#[derive(Debug)]
+struct SheetEntry {
+    name: String,
+    /// Worksheet partname, `None` for chartsheets and dialog sheets
+    partname: Option<PackURI>,
+    /// Pending edits keyed by 1-based `(row, col)`
+    edits: BTreeMap<(u32, u32), CellValue>,
+}
+
+/// An editable handle on an existing `.xlsx` workbook.
+///
+/// Edits are collected by [`set_cell`](Self::set_cell) and applied when the
+/// workbook is saved. Existing cells keep their style; their previous value
+/// and formula are replaced. Strings go into the shared string table when the
+/// workbook has one and are written inline otherwise.
+///
+/// Saving sets `fullCalcOnLoad` and removes `xl/calcChain.xml`, so Excel
+/// recalculates every formula that depends on the edited cells.
+#[derive(Debug)]
+pub struct WorkbookEditor {
+    /// The package bytes as they were opened
+    source: Vec<u8>,
+    /// Parsed package, used to resolve relationships
+    package: OpcPackage,
+    /// Partname of the workbook part
+    workbook_uri: PackURI,
+    /// Sheets in workbook order
+    sheets: Vec<SheetEntry>,
+    /// Parts rewritten by structural edits, keyed by partname
+    parts: HashMap<PackURI, String>,
+}
+
+impl WorkbookEditor {
+    /// Open a workbook file for editing.
+    pub fn open<P: AsRef<Path>>(path: P) -> SheetResult<Self> {
+        Self::from_bytes(std::fs::read(path)?)
+    }
+
+    /// Open a workbook held in memory for editing.
+    pub fn from_bytes(data: Vec<u8>) -> SheetResult<Self> {
+        let package = OpcPackage::from_bytes(&data)?;
+        let workbook_part = package.main_document_part()?;
+        let workbook_uri = workbook_part.partname().clone();
+
+        let content = std::str::from_utf8(workbook_part.try_blob()?)?;
+        let (infos, _, _) = workbook_parser::parse_workbook_xml(content)?;
+
+        let rels = workbook_part.rels();
+        let sheets = infos
+            .into_iter()
+            .map(|info| SheetEntry {
+                partname: rels
+                    .get(&info.relationship_id)
+                    .filter(|rel| rel.reltype() == rt::WORKSHEET)
+                    .and_then(|rel| rel.target_partname().ok()),
+                name: info.name,
+                edits: BTreeMap::new(),
+            })
+            .collect();
+
+        Ok(Self {
+            source: data,
+            package,
+            workbook_uri,
+            sheets,
+            parts: HashMap::new(),
+        })
+    }
+
+    /// Names of all sheets in workbook order.
+    pub fn worksheet_names(&self) -> Vec<&str> {
+        self.sheets
+            .iter()
+            .map(|entry| entry.name.as_str())
+            .collect()
+    }
+
+    /// Set the value of a cell.
+    ///
+    /// # Arguments
+    /// * `sheet` - Name of the worksheet
+    /// * `row` - Row number (1-based)
+    /// * `col` - Column number (1-based)
+    /// * `value` - The new value; [`CellValue::Empty`] clears the cell but
+    ///   keeps its style
+    ///
+    /// Formulas are written without a cached result and are computed by the
+    /// application when the file is opened. Dates get a date number format
+    /// unless the cell already shows dates, so they read back as
+    /// [`CellValue::DateTime`].
+    pub fn set_cell(
+        &mut self,
+        sheet: &str,
+        row: u32,
+        col: u32,
+        value: impl Into<CellValue>,
+    ) -> SheetResult<()> {
+        let index = self.worksheet_index(sheet)?;
+        self.set_cell_at(index, row, col, value.into())
+    }
+
+    /// Get a worksheet for editing, including row and column insertion
+    /// and deletion.
+    pub fn worksheet_mut(&mut self, name: &str) -> SheetResult<EditableWorksheet<'_>> {
+        let index = self.worksheet_index(name)?;
+        Ok(EditableWorksheet {
+            editor: self,
+            index,
+        })
+    }
+
+    fn worksheet_index(&self, name: &str) -> SheetResult<usize> {
+        let index = self
+            .sheets
+            .iter()
+            .position(|entry| entry.name == name)
+            .ok_or_else(|| format!("Worksheet '{}' not found", name))?;
+        if self.sheets[index].partname.is_none() {
+            return Err(format!("'{}' is not a worksheet", name).into());
+        }
+        Ok(index)
+    }
+
+    fn set_cell_at(
+        &mut self,
+        index: usize,
+        row: u32,
+        col: u32,
+        value: CellValue,
+    ) -> SheetResult<()> {
+        if !(1..=MAX_ROW).contains(&row) || !(1..=MAX_COL).contains(&col) {
+            return Err(format!("Cell ({}, {}) is outside the worksheet", row, col).into());
+        }
+
+        if let CellValue::Float(number) | CellValue::DateTime(number) = value
+            && !number.is_finite()
+        {
+            return Err("Cell values must be finite numbers".into());
+        }
+
+        self.sheets[index].edits.insert((row, col), value);
+        Ok(())
+    }
+
+    /// Current XML of a part, including structural edits made so far.
+    fn part_xml(&self, uri: &PackURI) -> SheetResult<String> {
+        match self.parts.get(uri) {
+            Some(xml) => Ok(xml.clone()),
+            None => Ok(std::str::from_utf8(self.package.get_part(uri)?.try_blob()?)?.to_string()),
+        }
+    }
+
+    /// Insert or delete rows or columns of a worksheet and move every
+    /// reference to them.
+    ///
+    /// All parts are rewritten before any is stored, so a failed edit leaves
+    /// the workbook unchanged.
+    fn shift(&mut self, index: usize, shift: Shift) -> SheetResult<()> {
+        let limit = match shift.axis {
+            Axis::Rows => MAX_ROW,
+            Axis::Columns => MAX_COL,
+        };
+        if shift.count == 0 {
+            return Ok(());
+        }
+        let last = shift.at.saturating_add(shift.count - 1);
+        if shift.at == 0 || shift.at > limit || (shift.delete && last > limit) {
+            return Err(format!(
+                "{} {}..{} is outside the worksheet",
+                if shift.axis == Axis::Rows {
+                    "Row"
+                } else {
+                    "Column"
+                },
+                shift.at,
+                last
+            )
+            .into());
+        }
+
+        let entry = &self.sheets[index];
+        let target = entry.name.clone();
+        let partname = entry.partname.clone().ok_or("Not a worksheet")?;
+        let fixup = Fixup {
+            shift,
+            target: &target,
+        };
+        let mut staged = Vec::new();
+        let mut headers = Vec::new();
+
+        for rel in self.package.get_part(&partname)?.rels().iter() {
+            let related = [
+                rt::TABLE,
+                rt::COMMENTS,
+                rt::THREADED_COMMENTS,
+                rt::PIVOT_TABLE,
+            ];
+            if rel.is_external() || !related.contains(&rel.reltype()) {
+                continue;
+            }
+            let uri = rel.target_partname()?;
+            let xml = self.part_xml(&uri)?;
+            let rewritten = match rel.reltype() {
+                rt::TABLE => {
+                    let (xml, new_headers) = structure::rewrite_table(&xml, &fixup.shift)?;
+                    headers.extend(new_headers);
+                    xml
+                },
+                rt::COMMENTS | rt::THREADED_COMMENTS => {
+                    structure::rewrite_comments(&xml, &fixup.shift)?
+                },
+                rt::PIVOT_TABLE => structure::rewrite_pivot_table(&xml, &fixup.shift)?,
+                _ => continue,
+            };
+            staged.push((uri, rewritten));
+        }
+
+        for entry in &self.sheets {
+            let Some(uri) = &entry.partname else {
+                continue;
+            };
+            let xml = self.part_xml(uri)?;
+            let rewritten = structure::rewrite_worksheet(&xml, &entry.name, &fixup)
+                .map_err(|e| format!("Worksheet '{}': {}", entry.name, e))?;
+            if rewritten != xml {
+                staged.push((uri.clone(), rewritten));
+            }
+        }
+
+        let mut workbook_parts = vec![(
+            self.workbook_uri.clone(),
+            structure::rewrite_defined_names as fn(&str, &Fixup) -> SheetResult<String>,
+        )];
+        for rel in self.package.get_part(&self.workbook_uri)?.rels().iter() {
+            if rel.reltype() == rt::PIVOT_CACHE_DEFINITION && !rel.is_external() {
+                workbook_parts.push((rel.target_partname()?, structure::rewrite_pivot_cache));
+            }
+        }
+        for part in self.package.iter_parts() {
+            if part.content_type() == ct::DML_CHART {
+                workbook_parts.push((part.partname().clone(), structure::rewrite_chart));
+            }
+        }
+        for (uri, rewrite) in workbook_parts {
+            let xml = self.part_xml(&uri)?;
+            let rewritten = rewrite(&xml, &fixup)?;
+            if rewritten != xml {
+                staged.push((uri, rewritten));
+            }
+        }
+
+        // Pending cell edits move along with the cells they were made to
+        for (position, entry) in self.sheets.iter_mut().enumerate() {
+            let edits = std::mem::take(&mut entry.edits);
+            entry.edits = edits
+                .into_iter()
+                .filter_map(|((row, col), mut value)| {
+                    if let CellValue::Formula {
+                        formula,
+                        array_range,
+                        ..
+                    } = &mut value
+                    {
+                        *formula = fixup.formula(formula, Some(&entry.name));
+                        if position == index
+                            && let Some(range) = array_range
+                        {
+                            *range = shift_range(range, &fixup.shift)?;
+                        }
+                    }
+                    if position == index {
+                        Some((fixup.shift.cell(row, col)?, value))
+                    } else {
+                        Some(((row, col), value))
+                    }
+                })
+                .collect();
+        }
+        for header in headers {
+            self.sheets[index]
+                .edits
+                .insert((header.row, header.col), CellValue::String(header.name));
+        }
+        self.parts.extend(staged);
+        Ok(())
+    }
+
+    /// Whether any cell has been set since the workbook was opened.
+    pub fn is_modified(&self) -> bool {
+        !self.parts.is_empty() || self.sheets.iter().any(|entry| !entry.edits.is_empty())
+    }
+
+    /// Serialize the edited workbook.
+    ///
+    /// Returns the original bytes unchanged when nothing was edited.
+    pub fn to_bytes(&self) -> SheetResult<Vec<u8>> {
+        if !self.is_modified() {
+            return Ok(self.source.clone());
+        }
+
+        let reader = PhysPkgReader::new(&self.source)?;
+        let mut editor = PhysPkgEditor::new(&self.source)?;
+        let rels = self.package.get_part(&self.workbook_uri)?.rels();
+
+        let shared_strings_uri = rels
+            .iter()
+            .find(|rel| rel.reltype() == rt::SHARED_STRINGS && !rel.is_external())
+            .map(|rel| rel.target_partname())
+            .transpose()?;
+        let mut shared_strings = match &shared_strings_uri {
+            Some(uri) => Some(SharedStringTable::parse(String::from_utf8(
+                reader.blob_for(uri)?,
+            )?)?),
+            None => None,
+        };
+
+        // Dates need a date number format to read back as dates
+        let has_dates = self.sheets.iter().any(|entry| {
+            entry
+                .edits
+                .values()
+                .any(|value| matches!(value, CellValue::DateTime(_)))
+        });
+        let styles_uri = rels
+            .iter()
+            .find(|rel| rel.reltype() == rt::STYLES && !rel.is_external())
+            .map(|rel| rel.target_partname())
+            .transpose()?;
+        let mut formats = match &styles_uri {
+            Some(uri) if has_dates => Some(CellFormats::parse(String::from_utf8(
+                reader.blob_for(uri)?,
+            )?)?),
+            None if has_dates => return Err("Workbook has no styles part for date cells".into()),
+            _ => None,
+        };
+
+        let mut parts = self.parts.clone();
+        for entry in &self.sheets {
+            let Some(partname) = entry.partname.as_ref().filter(|_| !entry.edits.is_empty()) else {
+                continue;
+            };
+            let xml = self.part_xml(partname)?;
+            let patched = patch_worksheet(
+                &xml,
+                &entry.edits,
+                shared_strings.as_mut(),
+                formats.as_mut(),
+            )
+            .map_err(|e| format!("Worksheet '{}': {}", entry.name, e))?;
+            parts.insert(partname.clone(), patched);
+        }
+        for (uri, xml) in &parts {
+            if *uri != self.workbook_uri {
+                editor.write(uri, xml.as_bytes());
+            }
+        }
+
+        if let (Some(uri), Some(table)) = (&shared_strings_uri, &shared_strings)
+            && table.is_modified()
+        {
+            editor.write(uri, table.to_xml()?.as_bytes());
+        }
+        if let (Some(uri), Some(formats)) = (&styles_uri, &formats)
+            && formats.is_modified()
+        {
+            editor.write(uri, formats.to_xml()?.as_bytes());
+        }
+
+        let workbook_xml = self.part_xml(&self.workbook_uri)?;
+        editor.write(
+            &self.workbook_uri,
+            set_full_calc_on_load(&workbook_xml)?.as_bytes(),
+        );
+
+        // The calculation chain lists formula cells; a stale one makes Excel
+        // repair the file, so drop it and let Excel rebuild it.
+        if let Some(rel) = rels
+            .iter()
+            .find(|rel| rel.reltype() == rt::CALC_CHAIN && !rel.is_external())
+        {
+            let calc_chain = rel.target_partname()?;
+            editor.remove(calc_chain.membername());
+
+            if let Some(rels_xml) = reader.rels_xml_for(&self.workbook_uri)? {
+                let rels_xml = String::from_utf8(rels_xml)?;
+                editor.write(
+                    &self.workbook_uri.rels_uri()?,
+                    remove_element(&rels_xml, b"Relationship", b"Id", rel.r_id())?.as_bytes(),
+                );
+            }
+
+            let content_types = String::from_utf8(reader.content_types_xml()?)?;
+            editor.write(
+                &PackURI::new("/[Content_Types].xml")?,
+                remove_element(
+                    &content_types,
+                    b"Override",
+                    b"PartName",
+                    calc_chain.as_str(),
+                )?
+                .as_bytes(),
+            );
+        }
+
+        Ok(editor.finish()?)
+    }
+
+    /// Save the edited workbook to a file.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> SheetResult<()> {
+        std::fs::write(path, self.to_bytes()?)?;
+        Ok(())
+    }
+}
+
+/// A worksheet of a [`WorkbookEditor`], borrowed for editing.
+///
+/// Inserting or deleting rows and columns moves the cells below or to the
+/// right and adjusts every reference to them the way Excel does: formulas
+/// on all sheets, shared formulas, merged ranges, conditional formatting,
+/// data validation, hyperlinks, auto filters, defined names, tables,
+/// comments, pivot table locations and sources, and chart series.
+/// References to deleted cells become `#REF!`, and ranges that lose some of
+/// their rows or columns shrink.
+///
+/// Edits that Excel refuses fail with an error and leave the workbook
+/// unchanged: deleting the header row of a table, cutting through a pivot
+/// table, or pushing cells off the end of the sheet. Drawing anchors are not
+/// moved.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use litchi::ooxml::xlsx::WorkbookEditor;
+///
+/// let mut editor = WorkbookEditor::open("report.xlsx")?;
+/// let mut sheet = editor.worksheet_mut("Summary")?;
+/// sheet.insert_rows(2, 3)?;
+/// sheet.set_cell(2, 1, "inserted")?;
+/// sheet.delete_columns(5, 1)?;
+/// editor.save("report-revised.xlsx")?;
+/// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+#[derive(Debug)]
+pub struct EditableWorksheet<'a> {
+    editor: &'a mut WorkbookEditor,
+    index: usize,
+}
+
+impl EditableWorksheet<'_> {
+    /// Name of the worksheet.
+    pub fn name(&self) -> &str {
+        &self.editor.sheets[self.index].name
+    }
+
+    /// Set the value of a cell, see [`WorkbookEditor::set_cell`].
+    pub fn set_cell(&mut self, row: u32, col: u32, value: impl Into<CellValue>) -> SheetResult<()> {
+        self.editor.set_cell_at(self.index, row, col, value.into())
+    }
+
+    /// Insert `count` empty rows before row `at` (1-based).
+    pub fn insert_rows(&mut self, at: u32, count: u32) -> SheetResult<()> {
+        self.shift(Axis::Rows, at, count, false)
+    }
+
+    /// Delete `count` rows starting at row `at` (1-based).
+    pub fn delete_rows(&mut self, at: u32, count: u32) -> SheetResult<()> {
+        self.shift(Axis::Rows, at, count, true)
+    }
+
+    /// Insert `count` empty columns before column `at` (1-based).
+    pub fn insert_columns(&mut self, at: u32, count: u32) -> SheetResult<()> {
+        self.shift(Axis::Columns, at, count, false)
+    }
+
+    /// Delete `count` columns starting at column `at` (1-based).
+    pub fn delete_columns(&mut self, at: u32, count: u32) -> SheetResult<()> {
+        self.shift(Axis::Columns, at, count, true)
+    }
+
+    fn shift(&mut self, axis: Axis, at: u32, count: u32, delete: bool) -> SheetResult<()> {
+        self.editor.shift(
+            self.index,
+            Shift {
+                axis,
+                at,
+                count,
+                delete,
+            },
+        )
+    }
+}
+
+/// Byte positions of an element in its part.
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    /// Start of the start tag
+    start: usize,
+    /// End of the start tag
+    tag_end: usize,
+    /// Start of the end tag, `None` for a self-closing element
+    close: Option<usize>,
+    /// End of the element
+    end: usize,
+}
+
+impl Span {
+    fn open(start: usize, tag_end: usize) -> Self {
+        Self {
+            start,
+            tag_end,
+            close: None,
+            end: tag_end,
+        }
+    }
+}
+
+/// A `<c>` element of a worksheet.
+#[derive(Debug)]
+struct ScannedCell {
+    col: u32,
+    start: usize,
+    end: usize,
+    /// Raw `s` attribute
+    style: Option<String>,
+    /// Whether the cell holds the master formula of a shared formula group
+    shared_master: bool,
+}
+
+/// A `<row>` element of a worksheet.
+#[derive(Debug)]
+struct ScannedRow {
+    row: u32,
+    span: Span,
+    /// Raw `spans` attribute
+    spans: Option<String>,
+    cells: Vec<ScannedCell>,
+}
+
+/// The parts of a worksheet that cell edits touch.
+#[derive(Debug, Default)]
+struct ScannedSheet {
+    /// Namespace prefix of SpreadsheetML elements, including the colon
+    prefix: String,
+    /// Span and `ref` of the `<dimension>` element
+    dimension: Option<(Span, String)>,
+    sheet_data: Option<Span>,
+    rows: Vec<ScannedRow>,
+}
+
+/// A cell range as 1-based `(min_col, min_row, max_col, max_row)`.
+type Bounds = (u32, u32, u32, u32);
+
+/// Locate rows and cells in a worksheet part.
+///
+/// Scanning stops at `</sheetData>`; nothing after it is needed for edits.
+fn scan_worksheet(xml: &str) -> SheetResult<ScannedSheet> {
+    let mut reader = Reader::from_str(xml);
+    let mut sheet = ScannedSheet::default();
+    let mut current: Option<ScannedRow> = None;
+    let mut last_row = 0;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let (e, empty) = match reader.read_event()? {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(e) if e.local_name().as_ref() == b"row" => {
+                if let Some(mut row) = current.take() {
+                    row.span.close = Some(start);
+                    row.span.end = reader.buffer_position() as usize;
+                    sheet.rows.push(row);
+                }
+                continue;
+            },
+            Event::End(e) if e.local_name().as_ref() == b"sheetData" => {
+                if let Some(data) = sheet.sheet_data.as_mut() {
+                    data.close = Some(start);
+                    data.end = reader.buffer_position() as usize;
+                }
+                break;
+            },
+            Event::Eof => break,
+            _ => continue,
+        };
+        let tag_end = reader.buffer_position() as usize;
+
+        match e.local_name().as_ref() {
+            b"dimension" if sheet.sheet_data.is_none() => {
+                if !empty {
+                    reader.read_to_end(e.name())?;
+                }
+                let mut span = Span::open(start, tag_end);
+                span.end = reader.buffer_position() as usize;
+                let range = attr(&e, b"ref").unwrap_or_default();
+                sheet.dimension = Some((span, range));
+            },
+            b"sheetData" => {
+                sheet.prefix = prefix_of(&e);
+                sheet.sheet_data = Some(Span::open(start, tag_end));
+                if empty {
+                    break;
+                }
+            },
+            b"row" if sheet.sheet_data.is_some() => {
+                let number = match attr(&e, b"r") {
+                    Some(r) => r
+                        .parse()
+                        .map_err(|_| format!("Invalid row number: {}", r))?,
+                    None => last_row + 1,
+                };
+                last_row = number;
+
+                let row = ScannedRow {
+                    row: number,
+                    span: Span::open(start, tag_end),
+                    spans: attr(&e, b"spans"),
+                    cells: Vec::new(),
+                };
+                if empty {
+                    sheet.rows.push(row);
+                } else {
+                    current = Some(row);
+                }
+            },
+            b"c" => {
+                let Some(row) = current.as_mut() else {
+                    return Err("Cell outside of a row".into());
+                };
+                let col = match attr(&e, b"r") {
+                    Some(reference) => Cell::reference_to_coords(&reference)?.0,
+                    None => row.cells.last().map_or(1, |cell| cell.col + 1),
+                };
+
+                let mut shared_master = false;
+                if !empty {
+                    loop {
+                        match reader.read_event()? {
+                            Event::Start(f) | Event::Empty(f)
+                                if f.local_name().as_ref() == b"f" =>
+                            {
+                                shared_master |= attr(&f, b"t").as_deref() == Some("shared")
+                                    && attr(&f, b"ref").is_some();
+                            },
+                            Event::End(end) if end.local_name().as_ref() == b"c" => break,
+                            Event::Eof => return Err("Unterminated cell".into()),
+                            _ => {},
+                        }
+                    }
+                }
+
+                row.cells.push(ScannedCell {
+                    col,
+                    start,
+                    end: reader.buffer_position() as usize,
+                    style: attr(&e, b"s"),
+                    shared_master,
+                });
+            },
+            _ => {},
+        }
+    }
+
+    Ok(sheet)
+}
+
+/// Apply cell edits to a worksheet part.
+fn patch_worksheet(
+    xml: &str,
+    edits: &BTreeMap<(u32, u32), CellValue>,
+    mut strings: Option<&mut SharedStringTable>,
+    mut formats: Option<&mut CellFormats>,
+) -> SheetResult<String> {
+    let sheet = scan_worksheet(xml)?;
+    let data = sheet.sheet_data.ok_or("Missing sheetData element")?;
+    let prefix = sheet.prefix.as_str();
+
+    let mut rows: BTreeMap<u32, Vec<(u32, &CellValue)>> = BTreeMap::new();
+    for (&(row, col), value) in edits {
+        rows.entry(row).or_default().push((col, value));
+    }
+
+    let mut bounds = sheet
+        .dimension
+        .as_ref()
+        .and_then(|(_, range)| parse_range(range));
+    let mut splices = Vec::new();
+    let mut appended_rows = String::new();
+
+    for (&number, cells) in &rows {
+        for &(col, value) in cells {
+            if !matches!(value, CellValue::Empty) {
+                bounds = Some(extend_bounds(bounds, col, number));
+            }
+        }
+
+        if let Some(row) = sheet.rows.iter().find(|row| row.row == number) {
+            patch_row(
+                xml,
+                row,
+                cells,
+                prefix,
+                &mut strings,
+                &mut formats,
+                &mut splices,
+            )?;
+            continue;
+        }
+
+        let mut content = String::new();
+        for &(col, value) in cells {
+            content.push_str(&cell_xml(
+                number,
+                col,
+                None,
+                value,
+                prefix,
+                strings.as_deref_mut(),
+                formats.as_deref_mut(),
+            ));
+        }
+        if content.is_empty() {
+            continue;
+        }
+
+        let row_xml = format!("<{prefix}row r=\"{number}\">{content}</{prefix}row>");
+        match data.close {
+            Some(close) => {
+                let at = sheet
+                    .rows
+                    .iter()
+                    .find(|row| row.row > number)
+                    .map_or(close, |row| row.span.start);
+                splices.push((at, at, row_xml));
+            },
+            None => appended_rows.push_str(&row_xml),
+        }
+    }
+
+    if !appended_rows.is_empty() {
+        splices.push((
+            data.start,
+            data.end,
+            format!("<{prefix}sheetData>{appended_rows}</{prefix}sheetData>"),
+        ));
+    }
+
+    if let (Some((span, _)), Some(bounds)) = (&sheet.dimension, bounds) {
+        splices.push((
+            span.start,
+            span.end,
+            format!("<{prefix}dimension ref=\"{}\"/>", format_range(bounds)),
+        ));
+    }
+
+    Ok(apply_splices(xml, splices))
+}
+
+/// Apply the edits of one row that already exists in the worksheet.
+fn patch_row(
+    xml: &str,
+    row: &ScannedRow,
+    cells: &[(u32, &CellValue)],
+    prefix: &str,
+    strings: &mut Option<&mut SharedStringTable>,
+    formats: &mut Option<&mut CellFormats>,
+    splices: &mut Vec<(usize, usize, String)>,
+) -> SheetResult<()> {
+    let number = row.row;
+    let spans = widened_spans(row.spans.as_deref(), cells);
+    let span_attrs: Vec<(&str, String)> = spans.into_iter().map(|s| ("spans", s)).collect();
+
+    let Some(close) = row.span.close else {
+        let mut content = String::new();
+        for &(col, value) in cells {
+            content.push_str(&cell_xml(
+                number,
+                col,
+                None,
+                value,
+                prefix,
+                strings.as_deref_mut(),
+                formats.as_deref_mut(),
+            ));
+        }
+        if !content.is_empty() {
+            let tag =
+                rewrite_start_tag(&xml[row.span.start..row.span.tag_end], &span_attrs, false)?;
+            splices.push((
+                row.span.start,
+                row.span.end,
+                format!("{tag}{content}</{prefix}row>"),
+            ));
+        }
+        return Ok(());
+    };
+
+    if !span_attrs.is_empty() {
+        let tag = rewrite_start_tag(&xml[row.span.start..row.span.tag_end], &span_attrs, false)?;
+        splices.push((row.span.start, row.span.tag_end, tag));
+    }
+
+    for &(col, value) in cells {
+        match row.cells.iter().find(|cell| cell.col == col) {
+            Some(cell) => {
+                if cell.shared_master {
+                    return Err(format!(
+                        "{}{} anchors a shared formula and cannot be replaced",
+                        Cell::column_to_letters(col),
+                        number
+                    )
+                    .into());
+                }
+                let replacement = cell_xml(
+                    number,
+                    col,
+                    cell.style.as_deref(),
+                    value,
+                    prefix,
+                    strings.as_deref_mut(),
+                    formats.as_deref_mut(),
+                );
+                splices.push((cell.start, cell.end, replacement));
+            },
+            None => {
+                let at = row
+                    .cells
+                    .iter()
+                    .find(|cell| cell.col > col)
+                    .map_or(close, |cell| cell.start);
+                let inserted = cell_xml(
+                    number,
+                    col,
+                    None,
+                    value,
+                    prefix,
+                    strings.as_deref_mut(),
+                    formats.as_deref_mut(),
+                );
+                splices.push((at, at, inserted));
+            },
+        }
+    }
+
+    Ok(())
+}
+
+/// The `spans` attribute of a row after new cells are added, if it changes.
+fn widened_spans(spans: Option<&str>, cells: &[(u32, &CellValue)]) -> Option<String> {
+    let ranges: Vec<(u32, u32)> = spans?
+        .split_whitespace()
+        .filter_map(|range| {
+            let (first, last) = range.split_once(':')?;
+            Some((first.parse().ok()?, last.parse().ok()?))
+        })
+        .collect();
+    let (mut min, mut max) = ranges
+        .iter()
+        .fold((u32::MAX, 0), |(min, max), &(first, last)| {
+            (min.min(first), max.max(last))
+        });
+
+    let mut changed = false;
+    for &(col, value) in cells {
+        let covered = ranges
+            .iter()
+            .any(|&(first, last)| (first..=last).contains(&col));
+        if !covered && !matches!(value, CellValue::Empty) {
+            min = min.min(col);
+            max = max.max(col);
+            changed = true;
+        }
+    }
+
+    changed.then(|| format!("{}:{}", min, max))
+}
+
+/// Serialize a cell, or return an empty string for an unstyled empty cell.
+fn cell_xml(
+    row: u32,
+    col: u32,
+    style: Option<&str>,
+    value: &CellValue,
+    prefix: &str,
+    strings: Option<&mut SharedStringTable>,
+    formats: Option<&mut CellFormats>,
+) -> String {
+    let date_style;
+    let style = match (value, formats) {
+        (CellValue::DateTime(serial), Some(formats)) => {
+            date_style = formats.date_style(style, *serial).to_string();
+            Some(date_style.as_str())
+        },
+        _ => style,
+    };
+
+    let reference = format!("{}{}", Cell::column_to_letters(col), row);
+    let mut attrs = format!("r=\"{}\"", reference);
+    if let Some(style) = style {
+        let _ = write!(attrs, " s=\"{}\"", style);
+    }
+
+    let v = |text: &str| format!("<{prefix}v>{text}</{prefix}v>");
+    let (kind, body) = match value {
+        CellValue::Empty => {
+            return match style {
+                Some(_) => format!("<{prefix}c {attrs}/>"),
+                None => String::new(),
+            };
+        },
+        CellValue::Bool(b) => (Some("b"), v(if *b { "1" } else { "0" })),
+        CellValue::Int(i) => (None, v(&i.to_string())),
+        CellValue::Float(n) | CellValue::DateTime(n) => (None, v(&n.to_string())),
+        CellValue::String(s) => match strings {
+            Some(table) => (Some("s"), v(&table.index_of(s).to_string())),
+            None => (
+                Some("inlineStr"),
+                format!("<{prefix}is>{}</{prefix}is>", text_element(prefix, s)),
+            ),
+        },
+        CellValue::Error(e) => (Some("e"), v(&escape_xml(e))),
+        CellValue::Formula {
+            formula,
+            is_array,
+            array_range,
+            ..
+        } => {
+            let formula = escape_xml(formula.strip_prefix('=').unwrap_or(formula));
+            let f = if *is_array {
+                let range = array_range.as_deref().unwrap_or(&reference);
+                format!(
+                    "<{prefix}f t=\"array\" ref=\"{}\">{formula}</{prefix}f>",
+                    escape_xml(range)
+                )
+            } else {
+                format!("<{prefix}f>{formula}</{prefix}f>")
+            };
+            (None, f)
+        },
+    };
+
+    if let Some(kind) = kind {
+        let _ = write!(attrs, " t=\"{}\"", kind);
+    }
+    format!("<{prefix}c {attrs}>{body}</{prefix}c>")
+}
+
+/// Serialize a `<t>` element, preserving surrounding whitespace.
+fn text_element(prefix: &str, text: &str) -> String {
+    let space = if text.trim() != text {
+        " xml:space=\"preserve\""
+    } else {
+        ""
+    };
+    format!("<{prefix}t{space}>{}</{prefix}t>", escape_xml(text))
+}
+
+/// The shared string table of a workbook being edited.
+#[derive(Debug)]
+struct SharedStringTable {
+    xml: String,
+    /// Namespace prefix of the `<sst>` element, including the colon
+    prefix: String,
+    /// The `<sst>` element
+    span: Span,
+    /// Index of each plain-text entry
+    lookup: HashMap<String, usize>,
+    /// Number of entries in the original table
+    unique: usize,
+    /// Original `count` attribute
+    count: Option<u64>,
+    /// Entries appended by edits
+    added: Vec<String>,
+    /// Number of cells that reference the table since it was opened
+    references: u64,
+}
+
+impl SharedStringTable {
+    fn parse(xml: String) -> SheetResult<Self> {
+        let mut span: Option<Span> = None;
+        let mut prefix = String::new();
+        let mut count = None;
+        let mut lookup = HashMap::new();
+        let mut unique = 0;
+
+        {
+            let mut reader = Reader::from_str(&xml);
+            let mut depth = 0usize;
+            let mut rich = false;
+            let mut text_start = 0;
+            let mut text: Option<(usize, usize)> = None;
+
+            loop {
+                let start = reader.buffer_position() as usize;
+                let event = reader.read_event()?;
+                let end = reader.buffer_position() as usize;
+                match event {
+                    Event::Start(e) => {
+                        depth += 1;
+                        match (depth, e.local_name().as_ref()) {
+                            (1, b"sst") => {
+                                prefix = prefix_of(&e);
+                                count = attr(&e, b"count").and_then(|c| c.parse().ok());
+                                span = Some(Span::open(start, end));
+                            },
+                            (2, b"si") => {
+                                rich = false;
+                                text = None;
+                            },
+                            (3, b"t") => text_start = end,
+                            (3, b"r") => rich = true,
+                            _ => {},
+                        }
+                    },
+                    Event::Empty(e) => match (depth + 1, e.local_name().as_ref()) {
+                        (1, b"sst") => {
+                            prefix = prefix_of(&e);
+                            count = attr(&e, b"count").and_then(|c| c.parse().ok());
+                            span = Some(Span::open(start, end));
+                        },
+                        (2, b"si") => {
+                            lookup.entry(String::new()).or_insert(unique);
+                            unique += 1;
+                        },
+                        (3, b"t") => text = Some((end, end)),
+                        _ => {},
+                    },
+                    Event::End(e) => {
+                        match (depth, e.local_name().as_ref()) {
+                            (1, b"sst") => {
+                                if let Some(span) = span.as_mut() {
+                                    span.close = Some(start);
+                                    span.end = end;
+                                }
+                            },
+                            (2, b"si") => {
+                                // Rich text entries never match a plain string
+                                if !rich {
+                                    let key = text
+                                        .map(|(first, last)| unescape_xml(&xml[first..last]))
+                                        .unwrap_or_default();
+                                    lookup.entry(key).or_insert(unique);
+                                }
+                                unique += 1;
+                            },
+                            (3, b"t") => text = Some((text_start, start)),
+                            _ => {},
+                        }
+                        depth = depth.saturating_sub(1);
+                    },
+                    Event::Eof => break,
+                    _ => {},
+                }
+            }
+        }
+
+        let span = span.ok_or("Missing sst element")?;
+        Ok(Self {
+            xml,
+            prefix,
+            span,
+            lookup,
+            unique,
+            count,
+            added: Vec::new(),
+            references: 0,
+        })
+    }
+
+    /// Index of a string in the table, appending it if needed.
+    fn index_of(&mut self, text: &str) -> usize {
+        self.references += 1;
+        if let Some(&index) = self.lookup.get(text) {
+            return index;
+        }
+
+        let index = self.unique + self.added.len();
+        self.added.push(text.to_string());
+        self.lookup.insert(text.to_string(), index);
+        index
+    }
+
+    fn is_modified(&self) -> bool {
+        self.references > 0
+    }
+
+    fn to_xml(&self) -> SheetResult<String> {
+        let prefix = self.prefix.as_str();
+        let mut attrs = vec![("uniqueCount", (self.unique + self.added.len()).to_string())];
+        if let Some(count) = self.count {
+            // Replaced cells may have referenced the table too, so the
+            // total is an upper bound; applications only use it as a hint.
+            attrs.push(("count", (count + self.references).to_string()));
+        }
+
+        let xml = self.xml.as_str();
+        let tag = rewrite_start_tag(&xml[self.span.start..self.span.tag_end], &attrs, false)?;
+        let entries: String = self
+            .added
+            .iter()
+            .map(|text| format!("<{prefix}si>{}</{prefix}si>", text_element(prefix, text)))
+            .collect();
+
+        let head = &xml[..self.span.start];
+        Ok(match self.span.close {
+            Some(close) => format!(
+                "{head}{tag}{}{entries}{}",
+                &xml[self.span.tag_end..close],
+                &xml[close..]
+            ),
+            None => format!(
+                "{head}{tag}{entries}</{prefix}sst>{}",
+                &xml[self.span.end..]
+            ),
+        })
+    }
+}
+
+/// The cell formats (`cellXfs`) of a workbook being edited.
+#[derive(Debug)]
+struct CellFormats {
+    xml: String,
+    /// Namespace prefix of the `<cellXfs>` element, including the colon
+    prefix: String,
+    /// The `<cellXfs>` element
+    span: Span,
+    /// Each `<xf>` element and its `numFmtId`
+    xfs: Vec<(Span, u32)>,
+    /// Codes of the custom number formats by ID
+    codes: HashMap<u32, String>,
+    /// Formats appended by edits
+    added: Vec<String>,
+    /// Date format added for each base format and number format ID
+    dates: HashMap<(usize, u32), usize>,
+}
+
+impl CellFormats {
+    fn parse(xml: String) -> SheetResult<Self> {
+        let mut span: Option<Span> = None;
+        let mut prefix = String::new();
+        let mut xfs: Vec<(Span, u32)> = Vec::new();
+        let mut codes = HashMap::new();
+
+        {
+            let mut reader = Reader::from_str(&xml);
+            let mut path: Vec<Vec<u8>> = Vec::new();
+
+            loop {
+                let start = reader.buffer_position() as usize;
+                let event = reader.read_event()?;
+                let end = reader.buffer_position() as usize;
+                let (e, empty) = match event {
+                    Event::Start(e) => (e, false),
+                    Event::Empty(e) => (e, true),
+                    Event::End(_) => {
+                        let name = path.pop().unwrap_or_default();
+                        if path.len() == 2 && name == b"xf" && path[1] == b"cellXfs" {
+                            if let Some((xf, _)) = xfs.last_mut() {
+                                xf.close = Some(start);
+                                xf.end = end;
+                            }
+                        } else if path.len() == 1
+                            && name == b"cellXfs"
+                            && let Some(span) = span.as_mut()
+                        {
+                            span.close = Some(start);
+                            span.end = end;
+                        }
+                        continue;
+                    },
+                    Event::Eof => break,
+                    _ => continue,
+                };
+
+                let local = e.local_name().as_ref().to_vec();
+                match (path.len(), local.as_slice()) {
+                    (1, b"cellXfs") if path[0] == b"styleSheet" => {
+                        prefix = prefix_of(&e);
+                        span = Some(Span::open(start, end));
+                    },
+                    (2, b"xf") if path[1] == b"cellXfs" => {
+                        let id = attr(&e, b"numFmtId").and_then(|id| id.parse().ok());
+                        xfs.push((Span::open(start, end), id.unwrap_or(0)));
+                    },
+                    (2, b"numFmt") if path[1] == b"numFmts" => {
+                        if let (Some(id), Some(code)) = (
+                            attr(&e, b"numFmtId").and_then(|id| id.parse().ok()),
+                            attr(&e, b"formatCode"),
+                        ) {
+                            codes.insert(id, code);
+                        }
+                    },
+                    _ => {},
+                }
+                if !empty {
+                    path.push(local);
+                }
+            }
+        }
+
+        let span = span.ok_or("Missing cellXfs element")?;
+        Ok(Self {
+            xml,
+            prefix,
+            span,
+            xfs,
+            codes,
+            added: Vec::new(),
+            dates: HashMap::new(),
+        })
+    }
+
+    /// Index of a format that shows `serial` as a date, based on the
+    /// format in `style`.
+    ///
+    /// A format that already shows dates is kept; otherwise a copy of it
+    /// with a date number format is appended.
+    fn date_style(&mut self, style: Option<&str>, serial: f64) -> usize {
+        let base = style.and_then(|s| s.parse().ok()).unwrap_or(0);
+        if let Some(&(_, id)) = self.xfs.get(base)
+            && is_date_format_id(id, self.codes.get(&id).map(String::as_str))
+        {
+            return base;
+        }
+
+        // Short date, or date and time when the serial has a time part
+        let id = if serial.fract() == 0.0 { 14 } else { 22 };
+        if let Some(&index) = self.dates.get(&(base, id)) {
+            return index;
+        }
+
+        let prefix = self.prefix.as_str();
+        let attrs = [
+            ("numFmtId", id.to_string()),
+            ("applyNumberFormat", "1".to_string()),
+        ];
+        let xf = self
+            .xfs
+            .get(base)
+            .and_then(|(span, _)| {
+                let xml = self.xml.as_str();
+                let tag = rewrite_start_tag(
+                    &xml[span.start..span.tag_end],
+                    &attrs,
+                    span.close.is_none(),
+                )
+                .ok()?;
+                Some(format!("{tag}{}", &xml[span.tag_end..span.end]))
+            })
+            .unwrap_or_else(|| {
+                format!(
+                    "<{prefix}xf numFmtId=\"{id}\" fontId=\"0\" fillId=\"0\" borderId=\"0\" xfId=\"0\" applyNumberFormat=\"1\"/>"
+                )
+            });
+
+        let index = self.xfs.len() + self.added.len();
+        self.added.push(xf);
+        self.dates.insert((base, id), index);
+        index
+    }
+
+    fn is_modified(&self) -> bool {
+        !self.added.is_empty()
+    }
+
+    fn to_xml(&self) -> SheetResult<String> {
+        let prefix = self.prefix.as_str();
+        let attrs = [("count", (self.xfs.len() + self.added.len()).to_string())];
+        let xml = self.xml.as_str();
+        let tag = rewrite_start_tag(&xml[self.span.start..self.span.tag_end], &attrs, false)?;
+        let formats = self.added.concat();
+
+        let head = &xml[..self.span.start];
+        Ok(match self.span.close {
+            Some(close) => format!(
+                "{head}{tag}{}{formats}{}",
+                &xml[self.span.tag_end..close],
+                &xml[close..]
+            ),
+            None => format!(
+                "{head}{tag}{formats}</{prefix}cellXfs>{}",
+                &xml[self.span.end..]
+            ),
+        })
+    }
+}
+
+/// Set `fullCalcOnLoad` on the `<calcPr>` element of a workbook part,
+/// adding the element if it is missing.
+fn set_full_calc_on_load(xml: &str) -> SheetResult<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut prefix = String::new();
+    let mut insert_at = None;
+    let mut depth = 0usize;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let (e, empty) = match reader.read_event()? {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(_) => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    insert_at.get_or_insert(start);
+                    break;
+                }
+                continue;
+            },
+            Event::Eof => break,
+            _ => continue,
+        };
+        let end = reader.buffer_position() as usize;
+
+        if depth == 0 {
+            prefix = prefix_of(&e);
+        } else if depth == 1 {
+            let name = e.local_name();
+            if name.as_ref() == b"calcPr" {
+                let tag = rewrite_start_tag(
+                    &xml[start..end],
+                    &[("fullCalcOnLoad", "1".to_string())],
+                    empty,
+                )?;
+                return Ok(format!("{}{tag}{}", &xml[..start], &xml[end..]));
+            }
+            if insert_at.is_none() && AFTER_CALC_PR.contains(&name.as_ref()) {
+                insert_at = Some(start);
+            }
+        }
+
+        if !empty {
+            depth += 1;
+        }
+    }
+
+    let at = insert_at.ok_or("Missing workbook element")?;
+    Ok(format!(
+        "{}<{prefix}calcPr fullCalcOnLoad=\"1\"/>{}",
+        &xml[..at],
+        &xml[at..]
+    ))
+}
+
+/// Remove the first `local` element whose `key` attribute equals `value`.
+fn remove_element(xml: &str, local: &[u8], key: &[u8], value: &str) -> SheetResult<String> {
+    let mut reader = Reader::from_str(xml);
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let (e, empty) = match reader.read_event()? {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::Eof => return Ok(xml.to_string()),
+            _ => continue,
+        };
+        if e.local_name().as_ref() != local {
+            continue;
+        }
+
+        let matches = attr(&e, key).is_some_and(|found| found.eq_ignore_ascii_case(value));
+        if matches {
+            if !empty {
+                reader.read_to_end(e.name())?;
+            }
+            let end = reader.buffer_position() as usize;
+            return Ok(format!("{}{}", &xml[..start], &xml[end..]));
+        }
+    }
+}
+
+/// Re-serialize a start tag with some attributes set.
+///
+/// Other attributes keep their raw values and order.
+fn rewrite_start_tag(
+    raw: &str,
+    attrs: &[(&str, String)],
+    self_closing: bool,
+) -> SheetResult<String> {
+    let mut reader = Reader::from_str(raw);
+    let e = match reader.read_event()? {
+        Event::Start(e) | Event::Empty(e) => e,
+        _ => return Err("Expected a start tag".into()),
+    };
+    let attrs: Vec<(&str, Option<String>)> = attrs
+        .iter()
+        .map(|(key, value)| (*key, Some(value.clone())))
+        .collect();
+    start_tag(&e, &attrs, self_closing)
+}
+
+/// Serialize a start tag with some attributes set, or removed when `None`.
+///
+/// Other attributes keep their raw values and order.
+fn start_tag(
+    e: &BytesStart,
+    attrs: &[(&str, Option<String>)],
+    self_closing: bool,
+) -> SheetResult<String> {
+    let mut tag = format!("<{}", std::str::from_utf8(e.name().as_ref())?);
+    let mut pending: Vec<&(&str, Option<String>)> = attrs.iter().collect();
+    for attr in e.attributes() {
+        let attr = attr?;
+        let key = std::str::from_utf8(attr.key.as_ref())?;
+        let value = match pending.iter().position(|(name, _)| *name == key) {
+            Some(index) => match &pending.remove(index).1 {
+                Some(value) => escape_xml(value),
+                None => continue,
+            },
+            None => String::from_utf8_lossy(&attr.value).replace('"', "&quot;"),
+        };
+        let _ = write!(tag, " {}=\"{}\"", key, value);
+    }
+    for (key, value) in pending {
+        if let Some(value) = value {
+            let _ = write!(tag, " {}=\"{}\"", key, escape_xml(value));
+        }
+    }
+
+    tag.push_str(if self_closing { "/>" } else { ">" });
+    Ok(tag)
+}
+
+/// Namespace prefix of an element, including the colon.
+fn prefix_of(e: &BytesStart) -> String {
+    e.name()
+        .prefix()
+        .map(|prefix| format!("{}:", String::from_utf8_lossy(prefix.as_ref())))
+        .unwrap_or_default()
+}
+
+/// Parse an `A1` or `A1:C3` range.
+fn parse_range(range: &str) -> Option<Bounds> {
+    let (first, last) = range.split_once(':').unwrap_or((range, range));
+    let (min_col, min_row) = Cell::reference_to_coords(first).ok()?;
+    let (max_col, max_row) = Cell::reference_to_coords(last).ok()?;
+    (min_col > 0 && min_row > 0).then_some((min_col, min_row, max_col, max_row))
+}
+
+fn extend_bounds(bounds: Option<Bounds>, col: u32, row: u32) -> Bounds {
+    match bounds {
+        Some((min_col, min_row, max_col, max_row)) => (
+            min_col.min(col),
+            min_row.min(row),
+            max_col.max(col),
+            max_row.max(row),
+        ),
+        None => (col, row, col, row),
+    }
+}
+
+fn format_range((min_col, min_row, max_col, max_row): Bounds) -> String {
+    let first = format!("{}{}", Cell::column_to_letters(min_col), min_row);
+    if (min_col, min_row) == (max_col, max_row) {
+        first
+    } else {
+        format!("{}:{}{}", first, Cell::column_to_letters(max_col), max_row)
+    }
+}
+
+/// Replace byte ranges of `xml`.
+///
+/// Splices must not overlap; splices starting at the same position are
+/// applied in the order given.
+fn apply_splices(xml: &str, mut splices: Vec<(usize, usize, String)>) -> String {
+    splices.sort_by_key(|(start, _, _)| *start);
+
+    let extra: usize = splices.iter().map(|(_, _, text)| text.len()).sum();
+    let mut out = String::with_capacity(xml.len() + extra);
+    let mut cursor = 0;
+    for (start, end, text) in splices {
+        out.push_str(&xml[cursor..start]);
+        out.push_str(&text);
+        cursor = end;
+    }
+    out.push_str(&xml[cursor..]);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::opc::validation::validate_bytes;
+    use crate::ooxml::xlsx::Workbook;
+    use crate::sheet::WorkbookTrait;
+
+    fn fixture(name: &str) -> Vec<u8> {
+        std::fs::read(format!("test-data/ooxml/xlsx/{}", name)).unwrap()
+    }
+
+    fn patch(xml: &str, edits: &[((u32, u32), CellValue)]) -> SheetResult<String> {
+        let edits = edits.iter().cloned().collect();
+        patch_worksheet(xml, &edits, None, None)
+    }
+
+    /// Members whose decompressed content differs between two packages.
+    fn changed_members(before: &[u8], after: &[u8]) -> Vec<String> {
+        let before = PhysPkgReader::new(before).unwrap();
+        let after = PhysPkgReader::new(after).unwrap();
+        let mut changed: Vec<String> = before
+            .member_names()
+            .unwrap()
+            .into_iter()
+            .filter(|name| {
+                let uri = PackURI::new(format!("/{}", name)).unwrap();
+                !after.contains(&uri)
+                    || before.blob_for(&uri).unwrap() != after.blob_for(&uri).unwrap()
+            })
+            .collect();
+        changed.sort();
+        changed
+    }
+
+    #[test]
+    fn test_patch_replaces_and_inserts_cells() {
+        let xml = concat!(
+            r#"<worksheet><dimension ref="A1:C2"/><sheetData>"#,
+            r#"<row r="1" spans="1:3"><c r="A1" s="2"><v>1</v></c><c r="C1"><f>A1*2</f><v>2</v></c></row>"#,
+            r#"<row r="5"/>"#,
+            r#"</sheetData></worksheet>"#,
+        );
+        let patched = patch(
+            xml,
+            &[
+                ((1, 1), CellValue::Int(7)),
+                ((1, 2), CellValue::Bool(true)),
+                ((1, 5), CellValue::Float(1.5)),
+                ((3, 2), CellValue::String("a < b".to_string())),
+                ((5, 1), CellValue::Int(9)),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            patched,
+            concat!(
+                r#"<worksheet><dimension ref="A1:E5"/><sheetData>"#,
+                r#"<row r="1" spans="1:5"><c r="A1" s="2"><v>7</v></c><c r="B1" t="b"><v>1</v></c>"#,
+                r#"<c r="C1"><f>A1*2</f><v>2</v></c><c r="E1"><v>1.5</v></c></row>"#,
+                r#"<row r="3"><c r="B3" t="inlineStr"><is><t>a &lt; b</t></is></c></row>"#,
+                r#"<row r="5"><c r="A5"><v>9</v></c></row>"#,
+                r#"</sheetData></worksheet>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_patch_clear_keeps_style() {
+        let xml = r#"<sheetData><row r="1"><c r="A1" s="4"><v>1</v></c><c r="B1"><v>2</v></c></row></sheetData>"#;
+        let patched = patch(
+            xml,
+            &[((1, 1), CellValue::Empty), ((1, 2), CellValue::Empty)],
+        )
+        .unwrap();
+        assert_eq!(
+            patched,
+            r#"<sheetData><row r="1"><c r="A1" s="4"/></row></sheetData>"#
+        );
+    }
+
+    #[test]
+    fn test_patch_empty_sheet_and_formulas() {
+        let xml = r#"<x:worksheet xmlns:x="main"><x:sheetData/></x:worksheet>"#;
+        let formula = CellValue::Formula {
+            formula: "=SUM(A1:A2)".to_string(),
+            cached_value: Some(Box::new(CellValue::Int(3))),
+            is_array: false,
+            array_range: None,
+        };
+        let patched = patch(xml, &[((2, 1), formula)]).unwrap();
+        assert_eq!(
+            patched,
+            r#"<x:worksheet xmlns:x="main"><x:sheetData><x:row r="2"><x:c r="A2"><x:f>SUM(A1:A2)</x:f></x:c></x:row></x:sheetData></x:worksheet>"#
+        );
+    }
+
+    #[test]
+    fn test_patch_rejects_shared_formula_master() {
+        let xml = concat!(
+            r#"<sheetData><row r="1"><c r="A1"><f t="shared" ref="A1:A2" si="0">B1</f><v>1</v></c></row>"#,
+            r#"<row r="2"><c r="A2"><f t="shared" si="0"/><v>1</v></c></row></sheetData>"#,
+        );
+        assert!(patch(xml, &[((1, 1), CellValue::Int(1))]).is_err());
+        assert!(patch(xml, &[((2, 1), CellValue::Int(1))]).is_ok());
+    }
+
+    #[test]
+    fn test_shared_string_table() {
+        let xml = r#"<sst count="3" uniqueCount="3"><si><t>a</t></si><si><r><t>b</t></r></si><si><t>b</t></si></sst>"#;
+        let mut table = SharedStringTable::parse(xml.to_string()).unwrap();
+        assert_eq!(table.index_of("b"), 2);
+        assert_eq!(table.index_of("c"), 3);
+        assert_eq!(table.index_of("c"), 3);
+        assert_eq!(
+            table.to_xml().unwrap(),
+            r#"<sst count="6" uniqueCount="4"><si><t>a</t></si><si><r><t>b</t></r></si><si><t>b</t></si><si><t>c</t></si></sst>"#
+        );
+
+        let mut empty = SharedStringTable::parse(r#"<sst count="0"/>"#.to_string()).unwrap();
+        assert_eq!(empty.index_of(" x"), 0);
+        assert_eq!(
+            empty.to_xml().unwrap(),
+            r#"<sst count="1" uniqueCount="1"><si><t xml:space="preserve"> x</t></si></sst>"#
+        );
+    }
+
+    #[test]
+    fn test_cell_formats_date_style() {
+        let xml = concat!(
+            r#"<styleSheet><numFmts count="1"><numFmt numFmtId="164" formatCode="yyyy-mm-dd"/></numFmts>"#,
+            r#"<cellXfs count="3"><xf numFmtId="0" fontId="0"/><xf numFmtId="164" fontId="1"/>"#,
+            r#"<xf numFmtId="4" fontId="2"><alignment horizontal="left"/></xf></cellXfs></styleSheet>"#,
+        );
+        let mut formats = CellFormats::parse(xml.to_string()).unwrap();
+        assert_eq!(formats.date_style(Some("1"), 45000.0), 1);
+        assert!(!formats.is_modified());
+        assert_eq!(formats.date_style(None, 45000.0), 3);
+        assert_eq!(formats.date_style(Some("0"), 45000.0), 3);
+        assert_eq!(formats.date_style(None, 45000.5), 4);
+        assert_eq!(formats.date_style(Some("2"), 45000.0), 5);
+        assert_eq!(
+            formats.to_xml().unwrap(),
+            concat!(
+                r#"<styleSheet><numFmts count="1"><numFmt numFmtId="164" formatCode="yyyy-mm-dd"/></numFmts>"#,
+                r#"<cellXfs count="6"><xf numFmtId="0" fontId="0"/><xf numFmtId="164" fontId="1"/>"#,
+                r#"<xf numFmtId="4" fontId="2"><alignment horizontal="left"/></xf>"#,
+                r#"<xf numFmtId="14" fontId="0" applyNumberFormat="1"/>"#,
+                r#"<xf numFmtId="22" fontId="0" applyNumberFormat="1"/>"#,
+                r#"<xf numFmtId="14" fontId="2" applyNumberFormat="1"><alignment horizontal="left"/></xf>"#,
+                r#"</cellXfs></styleSheet>"#,
+            )
+        );
+        assert!(CellFormats::parse("<styleSheet/>".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_set_full_calc_on_load() {
+        assert_eq!(
+            set_full_calc_on_load(r#"<workbook><sheets/><calcPr calcId="1"/></workbook>"#).unwrap(),
+            r#"<workbook><sheets/><calcPr calcId="1" fullCalcOnLoad="1"/></workbook>"#
+        );
+        assert_eq!(
+            set_full_calc_on_load(r#"<workbook><sheets/><extLst><ext/></extLst></workbook>"#)
+                .unwrap(),
+            r#"<workbook><sheets/><calcPr fullCalcOnLoad="1"/><extLst><ext/></extLst></workbook>"#
+        );
+        assert_eq!(
+            set_full_calc_on_load(r#"<workbook><sheets/></workbook>"#).unwrap(),
+            r#"<workbook><sheets/><calcPr fullCalcOnLoad="1"/></workbook>"#
+        );
+    }
+
+    #[test]
+    fn test_edit_workbook_with_pivot_tables() {
+        let source = fixture("ExcelPivotTableSample.xlsx");
+        let mut editor = WorkbookEditor::from_bytes(source.clone()).unwrap();
+        assert_eq!(
+            editor.worksheet_names(),
+            ["Tabelle1", "Tabelle2", "Tabelle3"]
+        );
+        assert!(!editor.is_modified());
+        assert_eq!(editor.to_bytes().unwrap(), source);
+
+        editor.set_cell("Tabelle1", 2, 2, 100).unwrap();
+        editor.set_cell("Tabelle1", 3, 1, "x").unwrap();
+        editor.set_cell("Tabelle1", 6, 9, "new value").unwrap();
+        assert!(editor.set_cell("Missing", 1, 1, 1).is_err());
+        assert!(editor.set_cell("Tabelle1", 0, 1, 1).is_err());
+        assert!(editor.set_cell("Tabelle1", 1, 1, f64::NAN).is_err());
+        let edited = editor.to_bytes().unwrap();
+
+        assert_eq!(
+            changed_members(&source, &edited),
+            [
+                "xl/sharedStrings.xml",
+                "xl/workbook.xml",
+                "xl/worksheets/sheet1.xml"
+            ]
+        );
+        assert!(
+            validate_bytes(&edited)
+                .unwrap()
+                .iter()
+                .all(|issue| !issue.is_error())
+        );
+
+        let original = Workbook::new(OpcPackage::from_bytes(&source).unwrap()).unwrap();
+        let workbook = Workbook::new(OpcPackage::from_bytes(&edited).unwrap()).unwrap();
+        let sheet = workbook.worksheet_by_name("Tabelle1").unwrap();
+        assert_eq!(
+            sheet.cell_value(2, 2).unwrap().into_owned(),
+            CellValue::Int(100)
+        );
+        assert_eq!(
+            sheet.cell_value(3, 1).unwrap().into_owned(),
+            CellValue::String("x".to_string())
+        );
+        assert_eq!(
+            sheet.cell_value(6, 9).unwrap().into_owned(),
+            CellValue::String("new value".to_string())
+        );
+        assert_eq!(
+            sheet.cell_value(4, 1).unwrap().into_owned(),
+            CellValue::String("c".to_string())
+        );
+        assert_eq!(
+            workbook.pivot_tables().unwrap().len(),
+            original.pivot_tables().unwrap().len()
+        );
+    }
+
+    #[test]
+    fn test_set_date_cells() {
+        let source = fixture("ExcelPivotTableSample.xlsx");
+        let mut editor = WorkbookEditor::from_bytes(source.clone()).unwrap();
+        editor
+            .set_cell("Tabelle1", 2, 2, CellValue::DateTime(45000.0))
+            .unwrap();
+        editor
+            .set_cell("Tabelle1", 2, 3, CellValue::DateTime(45001.0))
+            .unwrap();
+        editor
+            .set_cell("Tabelle1", 1, 9, CellValue::DateTime(45002.25))
+            .unwrap();
+        let edited = editor.to_bytes().unwrap();
+
+        assert_eq!(
+            changed_members(&source, &edited),
+            [
+                "xl/styles.xml",
+                "xl/workbook.xml",
+                "xl/worksheets/sheet1.xml"
+            ]
+        );
+        assert!(
+            validate_bytes(&edited)
+                .unwrap()
+                .iter()
+                .all(|issue| !issue.is_error())
+        );
+
+        let reader = PhysPkgReader::new(&edited).unwrap();
+        let sheet_xml = String::from_utf8(
+            reader
+                .blob_for(&PackURI::new("/xl/worksheets/sheet1.xml").unwrap())
+                .unwrap(),
+        )
+        .unwrap();
+        assert!(sheet_xml.contains(r#"<c r="B2" s="6"><v>45000</v></c>"#));
+        assert!(sheet_xml.contains(r#"<c r="C2" s="1"><v>45001</v></c>"#));
+        assert!(sheet_xml.contains(r#"<c r="I1" s="5"><v>45002.25</v></c>"#));
+
+        let workbook = Workbook::new(OpcPackage::from_bytes(&edited).unwrap()).unwrap();
+        let sheet = workbook.worksheet_by_name("Tabelle1").unwrap();
+        let value = |row, col| sheet.cell_value(row, col).unwrap().into_owned();
+        assert_eq!(value(2, 2), CellValue::DateTime(45000.0));
+        assert_eq!(value(2, 3), CellValue::DateTime(45001.0));
+        assert_eq!(value(1, 9), CellValue::DateTime(45002.25));
+        assert_eq!(value(2, 6), CellValue::Int(11));
+    }
+
+    #[test]
+    fn test_insert_rows_and_columns() {
+        let source = fixture("ExcelPivotTableSample.xlsx");
+        let mut editor = WorkbookEditor::from_bytes(source.clone()).unwrap();
+        let mut sheet = editor.worksheet_mut("Tabelle1").unwrap();
+        assert_eq!(sheet.name(), "Tabelle1");
+        assert!(sheet.delete_rows(1, 1).is_err());
+        assert!(sheet.insert_rows(0, 1).is_err());
+        sheet.insert_rows(3, 2).unwrap();
+        sheet.insert_columns(6, 1).unwrap();
+        sheet.set_cell(3, 1, "inserted").unwrap();
+        let edited = editor.to_bytes().unwrap();
+
+        assert_eq!(
+            changed_members(&source, &edited),
+            [
+                "xl/pivotCache/pivotCacheDefinition1.xml",
+                "xl/sharedStrings.xml",
+                "xl/tables/table1.xml",
+                "xl/workbook.xml",
+                "xl/worksheets/sheet1.xml"
+            ]
+        );
+        assert!(
+            validate_bytes(&edited)
+                .unwrap()
+                .iter()
+                .all(|issue| !issue.is_error())
+        );
+
+        let reader = PhysPkgReader::new(&edited).unwrap();
+        let member = |name: &str| {
+            String::from_utf8(reader.blob_for(&PackURI::new(name).unwrap()).unwrap()).unwrap()
+        };
+        assert!(
+            member("/xl/pivotCache/pivotCacheDefinition1.xml")
+                .contains(r#"<worksheetSource ref="A1:C6" sheet="Tabelle1"/>"#)
+        );
+        let table = member("/xl/tables/table1.xml");
+        assert!(table.contains(r#"ref="E1:H6""#));
+        assert!(table.contains(r#"<tableColumns count="4">"#));
+        assert!(table.contains(r#"<tableColumn id="4" name="Column1"/>"#));
+        assert!(member("/xl/worksheets/sheet1.xml").contains(r#"<dimension ref="A1:H6"/>"#));
+
+        let workbook = Workbook::new(OpcPackage::from_bytes(&edited).unwrap()).unwrap();
+        let sheet = workbook.worksheet_by_name("Tabelle1").unwrap();
+        let value = |row, col| sheet.cell_value(row, col).unwrap().into_owned();
+        assert_eq!(value(6, 1), CellValue::String("c".to_string()));
+        assert_eq!(value(3, 1), CellValue::String("inserted".to_string()));
+        assert_eq!(value(1, 6), CellValue::String("Column1".to_string()));
+        assert_eq!(value(6, 8), CellValue::DateTime(44878.0));
+        assert_eq!(value(4, 2), CellValue::Empty);
+    }
+
+    #[test]
+    fn test_delete_rows_and_columns_with_formulas() {
+        let source = fixture("StructuredRefs-lots-with-lookups.xlsx");
+        let mut editor = WorkbookEditor::from_bytes(source.clone()).unwrap();
+        let mut sheet = editor.worksheet_mut("Profile").unwrap();
+        sheet.delete_rows(3, 2).unwrap();
+        sheet.delete_columns(2, 1).unwrap();
+        sheet.insert_columns(1, 2).unwrap();
+        let edited = editor.to_bytes().unwrap();
+
+        assert_eq!(
+            changed_members(&source, &edited),
+            [
+                "[Content_Types].xml",
+                "xl/_rels/workbook.xml.rels",
+                "xl/calcChain.xml",
+                "xl/charts/chart1.xml",
+                "xl/workbook.xml",
+                "xl/worksheets/sheet1.xml"
+            ]
+        );
+        assert!(
+            validate_bytes(&edited)
+                .unwrap()
+                .iter()
+                .all(|issue| !issue.is_error())
+        );
+
+        let reader = PhysPkgReader::new(&edited).unwrap();
+        let member = |name: &str| {
+            String::from_utf8(reader.blob_for(&PackURI::new(name).unwrap()).unwrap()).unwrap()
+        };
+        let chart = member("/xl/charts/chart1.xml");
+        assert!(chart.contains("<c:f>Profile!$D$21:$E$21</c:f>"));
+        assert!(chart.contains("<c:f>Profile!#REF!</c:f>"));
+        assert!(member("/xl/workbook.xml").contains(
+            r#"<definedName name="_xlnm.Print_Area" localSheetId="0">Profile!$C$3:$P$36</definedName>"#
+        ));
+        // The shared formula of D9:D13 is expanded as it moves to E7:E11
+        let sheet = member("/xl/worksheets/sheet1.xml");
+        assert!(sheet.contains(r#"<f>IF(D7=" "," ",F7/D7*1000)</f>"#));
+        assert!(!sheet.contains(r#"t="shared""#));
+    }
+
+    #[test]
+    fn test_edit_drops_calc_chain() {
+        let source = fixture("StructuredRefs-lots-with-lookups.xlsx");
+        let mut editor = WorkbookEditor::from_bytes(source.clone()).unwrap();
+        editor.set_cell("dropdowns", 60, 1, "Edited").unwrap();
+        let edited = editor.to_bytes().unwrap();
+
+        assert_eq!(
+            changed_members(&source, &edited),
+            [
+                "[Content_Types].xml",
+                "xl/_rels/workbook.xml.rels",
+                "xl/calcChain.xml",
+                "xl/sharedStrings.xml",
+                "xl/workbook.xml",
+                "xl/worksheets/sheet4.xml"
+            ]
+        );
+        assert!(
+            validate_bytes(&edited)
+                .unwrap()
+                .iter()
+                .all(|issue| !issue.is_error())
+        );
+
+        let reader = PhysPkgReader::new(&edited).unwrap();
+        let workbook_xml = reader
+            .blob_for(&PackURI::new("/xl/workbook.xml").unwrap())
+            .unwrap();
+        assert!(
+            String::from_utf8(workbook_xml)
+                .unwrap()
+                .contains(r#"<calcPr calcId="162913" fullCalcOnLoad="1"/>"#)
+        );
+        assert!(
+            !String::from_utf8(reader.content_types_xml().unwrap())
+                .unwrap()
+                .contains("calcChain")
+        );
+
+        let sheet_xml = reader
+            .blob_for(&PackURI::new("/xl/worksheets/sheet4.xml").unwrap())
+            .unwrap();
+        assert!(
+            String::from_utf8(sheet_xml)
+                .unwrap()
+                .contains(r#"<dimension ref="A1:G60"/>"#)
+        );
+
+        let original = Workbook::new(OpcPackage::from_bytes(&source).unwrap()).unwrap();
+        let workbook = Workbook::new(OpcPackage::from_bytes(&edited).unwrap()).unwrap();
+        assert_eq!(
+            workbook
+                .shared_strings()
+                .get(original.shared_strings().len()),
+            Some("Edited")
+        );
+        assert_eq!(
+            workbook.charts().unwrap().len(),
+            original.charts().unwrap().len()
+        );
+    }
+}
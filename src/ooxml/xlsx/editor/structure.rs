@@ -0,0 +1,881 @@
+//! Rewriting parts for structural edits.
+//!
+//! Each function takes the XML of one part and returns it with the
+//! references to the edited sheet moved by a [`Shift`]. Elements anchored
+//! entirely inside deleted rows or columns are dropped.
+
+use std::collections::HashMap;
+
+use quick_xml::Reader;
+use quick_xml::escape::{partial_escape, unescape};
+use quick_xml::events::{BytesStart, Event};
+
+use crate::common::xml::attr;
+use crate::sheet::Result as SheetResult;
+
+use super::{Cell, apply_splices, prefix_of, start_tag};
+use crate::ooxml::xlsx::references::{
+    Area, Axis, Shift, map_references, parse_range, same_sheet, shift_range, shift_sqref, translate,
+};
+
+/// A structural edit of one sheet, as seen from the rest of the workbook.
+pub(super) struct Fixup<'a> {
+    pub shift: Shift,
+    /// Name of the edited sheet
+    pub target: &'a str,
+}
+
+impl Fixup<'_> {
+    fn is_target(&self, qualifier: Option<&str>, sheet: Option<&str>) -> bool {
+        qualifier
+            .or(sheet)
+            .is_some_and(|name| same_sheet(name, self.target))
+    }
+
+    /// Move the references to the edited sheet in a formula.
+    ///
+    /// Unqualified references point to `sheet`, the sheet the formula is
+    /// written on; `None` for workbook-level formulas.
+    pub fn formula(&self, formula: &str, sheet: Option<&str>) -> String {
+        map_references(formula, |qualifier, area| {
+            if self.is_target(qualifier, sheet) {
+                self.shift.area(area)
+            } else {
+                Some(area)
+            }
+        })
+    }
+
+    /// Whether a formula refers to the edited sheet.
+    fn refers_to_target(&self, formula: &str, sheet: Option<&str>) -> bool {
+        let mut found = false;
+        map_references(formula, |qualifier, area| {
+            found |= self.is_target(qualifier, sheet);
+            Some(area)
+        });
+        found
+    }
+}
+
+/// What happens to an element visited by [`rewrite_elements`].
+#[derive(Default)]
+struct Visit {
+    /// Remove the element and its content
+    drop: bool,
+    /// Attributes to set, or to remove when `None`
+    set: Vec<(&'static str, Option<String>)>,
+    /// Markup to insert before the element
+    before: String,
+}
+
+/// Rewrite the start tags of a part.
+fn rewrite_elements(
+    xml: &str,
+    mut visit: impl FnMut(&BytesStart) -> SheetResult<Visit>,
+) -> SheetResult<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut splices = Vec::new();
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let (e, empty) = match reader.read_event()? {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::Eof => break,
+            _ => continue,
+        };
+        let tag_end = reader.buffer_position() as usize;
+
+        let Visit { drop, set, before } = visit(&e)?;
+        if !before.is_empty() {
+            splices.push((start, start, before));
+        }
+        if drop {
+            if !empty {
+                reader.read_to_end(e.name())?;
+            }
+            splices.push((start, reader.buffer_position() as usize, String::new()));
+        } else if !set.is_empty() {
+            splices.push((start, tag_end, start_tag(&e, &set, empty)?));
+        }
+    }
+
+    Ok(apply_splices(xml, splices))
+}
+
+/// Rewrite the text of every element with one of the given local names.
+fn rewrite_text(xml: &str, locals: &[&[u8]], map: impl Fn(&str) -> String) -> SheetResult<String> {
+    let mut reader = Reader::from_str(xml);
+    let mut splices = Vec::new();
+
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) if locals.contains(&e.local_name().as_ref()) => {
+                let span = reader.read_to_end(e.name())?;
+                let (start, end) = (span.start as usize, span.end as usize);
+                let text = unescape(&xml[start..end])?;
+                let mapped = map(&text);
+                if mapped != text {
+                    splices.push((start, end, partial_escape(&mapped).into_owned()));
+                }
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok(apply_splices(xml, splices))
+}
+
+/// Bounds of a cell range as `(first_col, first_row, last_col, last_row)`.
+fn bounds(area: &Area) -> Option<(u32, u32, u32, u32)> {
+    let last = area.last.unwrap_or(area.first);
+    Some((
+        area.first.col?.index,
+        area.first.row?.index,
+        last.col?.index,
+        last.row?.index,
+    ))
+}
+
+/// Adjust the `colId` of a filter column, `None` if its column is deleted.
+///
+/// `first` is the first column of the filtered range before and after the
+/// edit.
+fn filter_column_id(col_id: &str, first: (u32, u32), shift: &Shift) -> Option<Option<String>> {
+    if shift.axis != Axis::Columns {
+        return Some(None);
+    }
+    let Ok(col_id) = col_id.parse::<u32>() else {
+        return Some(None);
+    };
+    let col = shift.index(first.0 + col_id)?;
+    Some(Some((col - first.1).to_string()))
+}
+
+/// The first column of a range before and after a shift.
+fn first_columns(range: &str, shift: &Shift) -> Option<(u32, u32)> {
+    let area = parse_range(range)?;
+    let shifted = shift.area(area)?;
+    Some((area.first.col?.index, shifted.first.col?.index))
+}
+
+/// A shared formula group, keyed by its `si` index.
+struct SharedFormula {
+    row: u32,
+    col: u32,
+    formula: String,
+}
+
+/// Tracks the position of rows and cells, which may omit their reference.
+#[derive(Default)]
+struct Cursor {
+    row: u32,
+    col: u32,
+}
+
+impl Cursor {
+    fn enter_row(&mut self, e: &BytesStart) -> SheetResult<()> {
+        self.row = match attr(e, b"r") {
+            Some(r) => r
+                .parse()
+                .map_err(|_| format!("Invalid row number: {}", r))?,
+            None => self.row + 1,
+        };
+        self.col = 0;
+        Ok(())
+    }
+
+    fn enter_cell(&mut self, e: &BytesStart) -> SheetResult<()> {
+        self.col = match attr(e, b"r") {
+            Some(reference) => Cell::reference_to_coords(&reference)?.0,
+            None => self.col + 1,
+        };
+        Ok(())
+    }
+}
+
+/// Collect the master formula of each shared formula group.
+fn shared_formulas(xml: &str) -> SheetResult<HashMap<String, SharedFormula>> {
+    let mut masters = HashMap::new();
+    if !xml.contains("\"shared\"") {
+        return Ok(masters);
+    }
+
+    let mut reader = Reader::from_str(xml);
+    let mut cursor = Cursor::default();
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"row" => {
+                cursor.enter_row(&e)?;
+            },
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == b"c" => {
+                cursor.enter_cell(&e)?;
+            },
+            Event::Start(e) if e.local_name().as_ref() == b"f" => {
+                let span = reader.read_to_end(e.name())?;
+                if attr(&e, b"t").as_deref() == Some("shared")
+                    && attr(&e, b"ref").is_some()
+                    && let Some(si) = attr(&e, b"si")
+                {
+                    let formula = unescape(&xml[span.start as usize..span.end as usize])?;
+                    masters.insert(
+                        si,
+                        SharedFormula {
+                            row: cursor.row,
+                            col: cursor.col,
+                            formula: formula.into_owned(),
+                        },
+                    );
+                }
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+    Ok(masters)
+}
+
+/// Excel refuses insertions that would move cells past the sheet's end.
+const PUSHED_OFF: &str = "The insertion would push cells off the worksheet";
+
+/// Elements whose children may all be dropped, which must then go too.
+const CONTAINERS: &[&[u8]] = &[b"mergeCells", b"dataValidations", b"hyperlinks"];
+
+/// Children of [`CONTAINERS`].
+const CONTAINED: &[&[u8]] = &[b"mergeCell", b"dataValidation", b"hyperlink"];
+
+/// Elements holding a formula as their text.
+const FORMULAS: &[&[u8]] = &[b"f", b"formula", b"formula1", b"formula2"];
+
+/// Rewrite a worksheet for a structural edit.
+///
+/// On the edited sheet itself rows and cells move and every range is
+/// adjusted. On other sheets only formulas pointing at the edited sheet
+/// change. Shared formulas that would end up with inconsistent references
+/// are expanded into ordinary formulas.
+pub(super) fn rewrite_worksheet(xml: &str, sheet: &str, fixup: &Fixup) -> SheetResult<String> {
+    let structural = same_sheet(sheet, fixup.target);
+    let shift = &fixup.shift;
+    let shared = shared_formulas(xml)?;
+    let expand: HashMap<&str, &SharedFormula> = shared
+        .iter()
+        .filter(|(_, group)| structural || fixup.refers_to_target(&group.formula, Some(sheet)))
+        .map(|(si, group)| (si.as_str(), group))
+        .collect();
+
+    let mut reader = Reader::from_str(xml);
+    // Output, with one extra level per open container
+    let mut out = vec![String::with_capacity(xml.len())];
+    let mut containers: Vec<(BytesStart, usize)> = Vec::new();
+    let mut cursor = Cursor::default();
+    let mut filter_columns = None;
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event()?;
+        let end = reader.buffer_position() as usize;
+        let top = out.len() - 1;
+
+        let (e, empty) = match event {
+            Event::Start(e) => (e, false),
+            Event::Empty(e) => (e, true),
+            Event::End(e) if structural && CONTAINERS.contains(&e.local_name().as_ref()) => {
+                let Some((tag, kept)) = containers.pop() else {
+                    out[top].push_str(&xml[start..end]);
+                    continue;
+                };
+                let content = out.pop().unwrap_or_default();
+                if kept > 0 {
+                    let mut set = Vec::new();
+                    if attr(&tag, b"count").is_some() {
+                        set.push(("count", Some(kept.to_string())));
+                    }
+                    let parent = out.last_mut().ok_or("Unbalanced worksheet XML")?;
+                    parent.push_str(&start_tag(&tag, &set, false)?);
+                    parent.push_str(&content);
+                    parent.push_str(&xml[start..end]);
+                }
+                continue;
+            },
+            Event::Eof => break,
+            _ => {
+                out[top].push_str(&xml[start..end]);
+                continue;
+            },
+        };
+
+        let local = e.local_name();
+        let local = local.as_ref();
+        let mut set: Vec<(&str, Option<String>)> = Vec::new();
+        let mut drop = false;
+
+        if FORMULAS.contains(&local) {
+            let (text, element_end) = if empty {
+                (String::new(), end)
+            } else {
+                let span = reader.read_to_end(e.name())?;
+                let text = unescape(&xml[span.start as usize..span.end as usize])?;
+                (text.into_owned(), reader.buffer_position() as usize)
+            };
+
+            let group = match (attr(&e, b"t"), attr(&e, b"si")) {
+                (Some(t), Some(si)) if local == b"f" && t == "shared" => expand.get(si.as_str()),
+                _ => None,
+            };
+            let formula = match group {
+                Some(group) => {
+                    set.extend([("t", None), ("ref", None), ("si", None)]);
+                    if attr(&e, b"ref").is_some() {
+                        text.clone()
+                    } else {
+                        translate(
+                            &group.formula,
+                            i64::from(cursor.row) - i64::from(group.row),
+                            i64::from(cursor.col) - i64::from(group.col),
+                        )
+                    }
+                },
+                None => {
+                    if structural
+                        && let Some(range) = attr(&e, b"ref")
+                        && let Some(shifted) = shift_range(&range, shift)
+                        && shifted != range
+                    {
+                        set.push(("ref", Some(shifted)));
+                    }
+                    text.clone()
+                },
+            };
+
+            let shifted = fixup.formula(&formula, Some(sheet));
+            if set.is_empty() && shifted == text {
+                out[top].push_str(&xml[start..element_end]);
+            } else {
+                let name = std::str::from_utf8(e.name().as_ref())?.to_string();
+                out[top].push_str(&start_tag(&e, &set, false)?);
+                out[top].push_str(&partial_escape(&shifted));
+                out[top].push_str(&format!("</{}>", name));
+            }
+            continue;
+        }
+
+        match local {
+            b"row" => {
+                cursor.enter_row(&e)?;
+                if structural {
+                    match shift.cell(cursor.row, 1) {
+                        Some((row, _)) => set.push(("r", Some(row.to_string()))),
+                        None if !shift.delete && !empty => return Err(PUSHED_OFF.into()),
+                        None => drop = true,
+                    }
+                    if shift.axis == Axis::Columns {
+                        set.push(("spans", None));
+                    }
+                }
+            },
+            b"c" => {
+                cursor.enter_cell(&e)?;
+                if structural {
+                    match shift.cell(cursor.row, cursor.col) {
+                        Some((row, col)) => set.push((
+                            "r",
+                            Some(format!("{}{}", Cell::column_to_letters(col), row)),
+                        )),
+                        None if !shift.delete => return Err(PUSHED_OFF.into()),
+                        None => drop = true,
+                    }
+                }
+            },
+            b"hyperlink" => {
+                if let Some(location) = attr(&e, b"location") {
+                    let location = unescape(&location)?;
+                    let shifted = fixup.formula(&location, Some(sheet));
+                    if shifted != location {
+                        set.push(("location", Some(shifted)));
+                    }
+                }
+                if structural && let Some(range) = attr(&e, b"ref") {
+                    match shift_range(&range, shift) {
+                        Some(shifted) => set.push(("ref", Some(shifted))),
+                        None => drop = true,
+                    }
+                }
+            },
+            _ if !structural => {},
+            b"dimension" => {
+                if let Some(range) = attr(&e, b"ref") {
+                    let shifted = shift_range(&range, shift).unwrap_or_else(|| "A1".to_string());
+                    set.push(("ref", Some(shifted)));
+                }
+            },
+            b"col" if shift.axis == Axis::Columns => {
+                let min = attr(&e, b"min").and_then(|min| min.parse().ok());
+                let max = attr(&e, b"max").and_then(|max| max.parse().ok());
+                if let (Some(min), Some(max)) = (min, max) {
+                    match shift.span(min, max) {
+                        Some((min, max)) => {
+                            set.push(("min", Some(min.to_string())));
+                            set.push(("max", Some(max.to_string())));
+                        },
+                        None => drop = true,
+                    }
+                }
+            },
+            b"mergeCell" => {
+                if let Some(range) = attr(&e, b"ref") {
+                    // A merge shrunk to a single cell is no merge at all
+                    match shift_range(&range, shift).filter(|shifted| {
+                        parse_range(shifted)
+                            .is_some_and(|a| a.last.is_some_and(|last| last != a.first))
+                    }) {
+                        Some(shifted) => set.push(("ref", Some(shifted))),
+                        None => drop = true,
+                    }
+                }
+            },
+            b"conditionalFormatting" | b"dataValidation" => {
+                if let Some(sqref) = attr(&e, b"sqref") {
+                    match shift_sqref(&sqref, shift) {
+                        Some(shifted) => set.push(("sqref", Some(shifted))),
+                        None => drop = true,
+                    }
+                }
+            },
+            b"autoFilter" | b"sortState" | b"sortCondition" => {
+                if let Some(range) = attr(&e, b"ref") {
+                    if local == b"autoFilter" {
+                        filter_columns = first_columns(&range, shift);
+                    }
+                    match shift_range(&range, shift) {
+                        Some(shifted) => set.push(("ref", Some(shifted))),
+                        None => drop = true,
+                    }
+                }
+            },
+            b"filterColumn" => {
+                if let (Some(first), Some(col_id)) = (filter_columns, attr(&e, b"colId")) {
+                    match filter_column_id(&col_id, first, shift) {
+                        Some(Some(col_id)) => set.push(("colId", Some(col_id))),
+                        Some(None) => {},
+                        None => drop = true,
+                    }
+                }
+            },
+            b"sqref" if !empty => {
+                let span = reader.read_to_end(e.name())?;
+                let (text_start, text_end) = (span.start as usize, span.end as usize);
+                let element_end = reader.buffer_position() as usize;
+                // An emptied list cannot be written; keep the original then
+                let sqref = shift_sqref(&xml[text_start..text_end], shift)
+                    .unwrap_or_else(|| xml[text_start..text_end].to_string());
+                out[top].push_str(&xml[start..text_start]);
+                out[top].push_str(&sqref);
+                out[top].push_str(&xml[text_end..element_end]);
+                continue;
+            },
+            _ => {},
+        }
+
+        if drop {
+            if !empty {
+                reader.read_to_end(e.name())?;
+            }
+            continue;
+        }
+        if structural
+            && CONTAINED.contains(&local)
+            && let Some((_, kept)) = containers.last_mut()
+        {
+            *kept += 1;
+        }
+        if structural && !empty && CONTAINERS.contains(&local) {
+            containers.push((e.into_owned(), 0));
+            out.push(String::new());
+            continue;
+        }
+
+        let top = out.len() - 1;
+        if set.is_empty() {
+            out[top].push_str(&xml[start..end]);
+        } else {
+            out[top].push_str(&start_tag(&e, &set, empty)?);
+        }
+    }
+
+    Ok(out.concat())
+}
+
+/// Rewrite the defined names of a workbook part.
+pub(super) fn rewrite_defined_names(xml: &str, fixup: &Fixup) -> SheetResult<String> {
+    rewrite_text(xml, &[b"definedName"], |text| fixup.formula(text, None))
+}
+
+/// Rewrite the series and category references of a chart part.
+pub(super) fn rewrite_chart(xml: &str, fixup: &Fixup) -> SheetResult<String> {
+    rewrite_text(xml, &[b"f"], |text| fixup.formula(text, None))
+}
+
+/// Move the cell anchors of comments and threaded comments, dropping the
+/// comments on deleted cells.
+pub(super) fn rewrite_comments(xml: &str, shift: &Shift) -> SheetResult<String> {
+    rewrite_elements(xml, |e| {
+        let mut visit = Visit::default();
+        if matches!(e.local_name().as_ref(), b"comment" | b"threadedComment")
+            && let Some(range) = attr(e, b"ref")
+        {
+            match shift_range(&range, shift) {
+                Some(shifted) => visit.set.push(("ref", Some(shifted))),
+                None => visit.drop = true,
+            }
+        }
+        Ok(visit)
+    })
+}
+
+/// Move the location of a pivot table.
+///
+/// Fails if the edit cuts through the pivot table, which Excel refuses too.
+pub(super) fn rewrite_pivot_table(xml: &str, shift: &Shift) -> SheetResult<String> {
+    let mut name = String::new();
+    rewrite_elements(xml, |e| {
+        let mut visit = Visit::default();
+        match e.local_name().as_ref() {
+            b"pivotTableDefinition" => name = attr(e, b"name").unwrap_or_default(),
+            b"location" => {
+                if let Some(area) = attr(e, b"ref").as_deref().and_then(parse_range) {
+                    let size =
+                        |area: &Area| bounds(area).map(|(c1, r1, c2, r2)| (c2 - c1, r2 - r1));
+                    match shift.area(area) {
+                        Some(shifted) if size(&shifted) == size(&area) => {
+                            visit.set.push(("ref", Some(shifted.to_string())));
+                        },
+                        _ => {
+                            return Err(format!(
+                                "Cannot insert or delete cells inside pivot table '{}'",
+                                name
+                            )
+                            .into());
+                        },
+                    }
+                }
+            },
+            _ => {},
+        }
+        Ok(visit)
+    })
+}
+
+/// Move the source range of a pivot cache built on the edited sheet.
+pub(super) fn rewrite_pivot_cache(xml: &str, fixup: &Fixup) -> SheetResult<String> {
+    rewrite_elements(xml, |e| {
+        let mut visit = Visit::default();
+        if e.local_name().as_ref() == b"worksheetSource"
+            && attr(e, b"sheet").is_some_and(|sheet| same_sheet(&sheet, fixup.target))
+            && let Some(range) = attr(e, b"ref")
+            && let Some(shifted) = shift_range(&range, &fixup.shift)
+        {
+            visit.set.push(("ref", Some(shifted)));
+        }
+        Ok(visit)
+    })
+}
+
+/// A header cell for a column inserted into a table.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct NewHeader {
+    pub row: u32,
+    pub col: u32,
+    pub name: String,
+}
+
+/// Resize a table for a structural edit on its sheet.
+///
+/// Columns inserted inside the table become new table columns named like
+/// Excel names them; their header cells are returned so they can be written
+/// to the sheet. Fails when the edit would delete the header row or every
+/// row or column of the table.
+pub(super) fn rewrite_table(xml: &str, shift: &Shift) -> SheetResult<(String, Vec<NewHeader>)> {
+    let mut reader = Reader::from_str(xml);
+    let mut table = None;
+    let mut ids = Vec::new();
+    let mut names = Vec::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(e) | Event::Empty(e) => match e.local_name().as_ref() {
+                b"table" => table = Some(e.into_owned()),
+                b"tableColumn" => {
+                    ids.extend(attr(&e, b"id").and_then(|id| id.parse::<u32>().ok()));
+                    names.extend(attr(&e, b"name").map(|name| name.to_lowercase()));
+                },
+                _ => {},
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    let table = table.ok_or("Missing table element")?;
+    let name = attr(&table, b"displayName").unwrap_or_default();
+    let area = attr(&table, b"ref")
+        .as_deref()
+        .and_then(parse_range)
+        .ok_or_else(|| format!("Invalid range for table '{}'", name))?;
+    let (c1, r1, c2, r2) = bounds(&area).ok_or("Invalid table range")?;
+    let has_header = attr(&table, b"headerRowCount").as_deref() != Some("0");
+
+    let shifted = shift.area(area);
+    let removes_header = has_header && shift.axis == Axis::Rows && shift.index(r1).is_none();
+    let removes_rows = match shifted.as_ref().and_then(bounds) {
+        Some((_, first, _, last)) => has_header && first == last && r1 != r2,
+        None => true,
+    };
+    if removes_header || removes_rows {
+        return Err(format!(
+            "The edit would delete the header or all rows of table '{}'",
+            name
+        )
+        .into());
+    }
+    let shifted = shifted.ok_or("Invalid table range")?;
+
+    // Table columns deleted or inserted, by their index in the table
+    let width = c2 - c1 + 1;
+    let deleted: Vec<bool> = (c1..=c2)
+        .map(|col| shift.axis == Axis::Columns && shift.index(col).is_none())
+        .collect();
+    let insert_at =
+        (shift.axis == Axis::Columns && !shift.delete && shift.at > c1 && shift.at <= c2)
+            .then(|| shift.at - c1);
+
+    let mut headers = Vec::new();
+    let mut new_columns = String::new();
+    let mut prefix = String::new();
+    if let Some(index) = insert_at {
+        let mut next_id = ids.iter().copied().max().unwrap_or(0);
+        let mut suffix = 0;
+        for offset in 0..shift.count {
+            let name = loop {
+                suffix += 1;
+                let name = format!("Column{}", suffix);
+                if !names.contains(&name.to_lowercase()) {
+                    break name;
+                }
+            };
+            next_id += 1;
+            new_columns.push_str(&format!(
+                "<{{prefix}}tableColumn id=\"{}\" name=\"{}\"/>",
+                next_id, name
+            ));
+            if has_header {
+                headers.push(NewHeader {
+                    row: r1,
+                    col: c1 + index + offset,
+                    name,
+                });
+            }
+        }
+    }
+    let columns = width - deleted.iter().filter(|&&deleted| deleted).count() as u32
+        + if insert_at.is_some() { shift.count } else { 0 };
+
+    let mut index = 0;
+    let mut filter_columns = None;
+    let rewritten = rewrite_elements(xml, |e| {
+        let mut visit = Visit::default();
+        match e.local_name().as_ref() {
+            b"table" => visit.set.push(("ref", Some(shifted.to_string()))),
+            b"autoFilter" | b"sortState" | b"sortCondition" => {
+                if let Some(range) = attr(e, b"ref") {
+                    if e.local_name().as_ref() == b"autoFilter" {
+                        filter_columns = first_columns(&range, shift);
+                    }
+                    match shift_range(&range, shift) {
+                        Some(shifted) => visit.set.push(("ref", Some(shifted))),
+                        None => visit.drop = true,
+                    }
+                }
+            },
+            b"filterColumn" => {
+                if let (Some(first), Some(col_id)) = (filter_columns, attr(e, b"colId")) {
+                    match filter_column_id(&col_id, first, shift) {
+                        Some(Some(col_id)) => visit.set.push(("colId", Some(col_id))),
+                        Some(None) => {},
+                        None => visit.drop = true,
+                    }
+                }
+            },
+            b"tableColumns" => {
+                prefix = prefix_of(e);
+                visit.set.push(("count", Some(columns.to_string())));
+            },
+            b"tableColumn" => {
+                if insert_at == Some(index) {
+                    visit.before = new_columns.replace("{prefix}", &prefix);
+                }
+                visit.drop = deleted.get(index as usize).copied().unwrap_or(false);
+                index += 1;
+            },
+            _ => {},
+        }
+        Ok(visit)
+    })?;
+
+    Ok((rewritten, headers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixup(axis: Axis, at: u32, count: u32, delete: bool) -> Fixup<'static> {
+        Fixup {
+            shift: Shift {
+                axis,
+                at,
+                count,
+                delete,
+            },
+            target: "Data",
+        }
+    }
+
+    #[test]
+    fn test_rewrite_edited_sheet() {
+        let xml = concat!(
+            r#"<worksheet><dimension ref="A1:C6"/><sheetData>"#,
+            r#"<row r="1" spans="1:3"><c r="A1"><v>1</v></c><c r="B1"><f>SUM(A1:A6)</f><v>1</v></c></row>"#,
+            r#"<row r="3"><c r="A3"><f t="shared" ref="A3:A5" si="0">A1*2</f><v>2</v></c></row>"#,
+            r#"<row r="4"><c r="A4"><f t="shared" si="0"/><v>2</v></c></row>"#,
+            r#"<row r="5"><c r="A5"><f t="shared" si="0"/><v>2</v></c></row>"#,
+            r#"<row r="6"><c r="A6"><v>3</v></c></row>"#,
+            r#"</sheetData><mergeCells count="2"><mergeCell ref="B3:B4"/><mergeCell ref="A6:C6"/></mergeCells>"#,
+            r#"<conditionalFormatting sqref="A4"><cfRule type="expression" priority="1"><formula>A4&gt;1</formula></cfRule></conditionalFormatting>"#,
+            r#"<hyperlinks><hyperlink ref="A6" location="Data!A6"/></hyperlinks>"#,
+            r#"</worksheet>"#,
+        );
+        let rewritten = rewrite_worksheet(xml, "Data", &fixup(Axis::Rows, 4, 1, true)).unwrap();
+        assert_eq!(
+            rewritten,
+            concat!(
+                r#"<worksheet><dimension ref="A1:C5"/><sheetData>"#,
+                r#"<row r="1" spans="1:3"><c r="A1"><v>1</v></c><c r="B1"><f>SUM(A1:A5)</f><v>1</v></c></row>"#,
+                r#"<row r="3"><c r="A3"><f>A1*2</f><v>2</v></c></row>"#,
+                r#"<row r="4"><c r="A4"><f>A3*2</f><v>2</v></c></row>"#,
+                r#"<row r="5"><c r="A5"><v>3</v></c></row>"#,
+                r#"</sheetData><mergeCells count="1"><mergeCell ref="A5:C5"/></mergeCells>"#,
+                r#"<hyperlinks><hyperlink ref="A5" location="Data!A5"/></hyperlinks>"#,
+                r#"</worksheet>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_rewrite_columns() {
+        let xml = concat!(
+            r#"<worksheet><cols><col min="1" max="1" width="5"/><col min="2" max="4" width="9"/></cols>"#,
+            r#"<sheetData><row r="1" spans="1:3"><c r="A1"><v>1</v></c><c r="B1"><v>2</v></c><c r="C1"><f>A1+B1</f></c></row></sheetData>"#,
+            r#"<autoFilter ref="A1:C1"><filterColumn colId="2"/></autoFilter>"#,
+            r#"<dataValidations count="1"><dataValidation sqref="B1"><formula1>$B$1</formula1></dataValidation></dataValidations>"#,
+            r#"</worksheet>"#,
+        );
+        let rewritten = rewrite_worksheet(xml, "data", &fixup(Axis::Columns, 2, 1, true)).unwrap();
+        assert_eq!(
+            rewritten,
+            concat!(
+                r#"<worksheet><cols><col min="1" max="1" width="5"/><col min="2" max="3" width="9"/></cols>"#,
+                r#"<sheetData><row r="1"><c r="A1"><v>1</v></c><c r="B1"><f>A1+#REF!</f></c></row></sheetData>"#,
+                r#"<autoFilter ref="A1:B1"><filterColumn colId="1"/></autoFilter>"#,
+                r#"</worksheet>"#,
+            )
+        );
+    }
+
+    #[test]
+    fn test_rewrite_other_sheet() {
+        let xml = concat!(
+            r#"<worksheet><sheetData><row r="1">"#,
+            r#"<c r="A1"><f>Data!A5+A5</f></c><c r="B1"><f t="shared" ref="B1:B2" si="0">Data!B1</f></c></row>"#,
+            r#"<row r="2"><c r="B2"><f t="shared" si="0"/></c></row>"#,
+            r#"</sheetData></worksheet>"#,
+        );
+        let rewritten = rewrite_worksheet(xml, "Summary", &fixup(Axis::Rows, 2, 2, false)).unwrap();
+        assert_eq!(
+            rewritten,
+            concat!(
+                r#"<worksheet><sheetData><row r="1">"#,
+                r#"<c r="A1"><f>Data!A7+A5</f></c><c r="B1"><f>Data!B1</f></c></row>"#,
+                r#"<row r="2"><c r="B2"><f>Data!B4</f></c></row>"#,
+                r#"</sheetData></worksheet>"#,
+            )
+        );
+
+        let untouched = r#"<worksheet><sheetData><row r="1"><c r="A1"><f>Other!A5</f></c></row></sheetData></worksheet>"#;
+        assert_eq!(
+            rewrite_worksheet(untouched, "Summary", &fixup(Axis::Rows, 2, 2, false)).unwrap(),
+            untouched
+        );
+    }
+
+    #[test]
+    fn test_rewrite_table() {
+        let xml = concat!(
+            r#"<table id="1" name="T" displayName="T" ref="B2:D5"><autoFilter ref="B2:D5"><filterColumn colId="2"/></autoFilter>"#,
+            r#"<tableColumns count="3"><tableColumn id="1" name="A"/><tableColumn id="2" name="Column1"/><tableColumn id="3" name="C"/></tableColumns></table>"#,
+        );
+        let (rewritten, headers) =
+            rewrite_table(xml, &fixup(Axis::Columns, 3, 1, false).shift).unwrap();
+        assert_eq!(
+            rewritten,
+            concat!(
+                r#"<table id="1" name="T" displayName="T" ref="B2:E5"><autoFilter ref="B2:E5"><filterColumn colId="3"/></autoFilter>"#,
+                r#"<tableColumns count="4"><tableColumn id="1" name="A"/><tableColumn id="4" name="Column2"/><tableColumn id="2" name="Column1"/><tableColumn id="3" name="C"/></tableColumns></table>"#,
+            )
+        );
+        assert_eq!(
+            headers,
+            [NewHeader {
+                row: 2,
+                col: 3,
+                name: "Column2".to_string()
+            }]
+        );
+
+        let (rewritten, headers) =
+            rewrite_table(xml, &fixup(Axis::Columns, 3, 1, true).shift).unwrap();
+        assert!(rewritten.contains(r#"ref="B2:C5""#));
+        assert!(rewritten.contains(r#"<tableColumns count="2"><tableColumn id="1" name="A"/><tableColumn id="3" name="C"/>"#));
+        assert!(rewritten.contains(r#"<filterColumn colId="1"/>"#));
+        assert!(headers.is_empty());
+
+        assert!(rewrite_table(xml, &fixup(Axis::Rows, 2, 1, true).shift).is_err());
+        assert!(rewrite_table(xml, &fixup(Axis::Rows, 3, 3, true).shift).is_err());
+        let (rewritten, _) = rewrite_table(xml, &fixup(Axis::Rows, 3, 2, true).shift).unwrap();
+        assert!(rewritten.contains(r#"ref="B2:D3""#));
+    }
+
+    #[test]
+    fn test_rewrite_pivot_table() {
+        let xml = r#"<pivotTableDefinition name="P"><location ref="A3:D7" firstHeaderRow="1"/></pivotTableDefinition>"#;
+        assert_eq!(
+            rewrite_pivot_table(xml, &fixup(Axis::Rows, 1, 2, false).shift).unwrap(),
+            r#"<pivotTableDefinition name="P"><location ref="A5:D9" firstHeaderRow="1"/></pivotTableDefinition>"#
+        );
+        assert!(rewrite_pivot_table(xml, &fixup(Axis::Rows, 5, 1, false).shift).is_err());
+    }
+
+    #[test]
+    fn test_rewrite_comments_and_names() {
+        let comments = r#"<comments><commentList><comment ref="B2"><text/></comment><comment ref="B5"><text/></comment></commentList></comments>"#;
+        assert_eq!(
+            rewrite_comments(comments, &fixup(Axis::Rows, 2, 1, true).shift).unwrap(),
+            r#"<comments><commentList><comment ref="B4"><text/></comment></commentList></comments>"#
+        );
+
+        let workbook = r#"<workbook><definedNames><definedName name="x">Data!$A$1:$A$9</definedName><definedName name="y">'Other'!$A$9</definedName></definedNames></workbook>"#;
+        assert_eq!(
+            rewrite_defined_names(workbook, &fixup(Axis::Rows, 2, 1, true)).unwrap(),
+            r#"<workbook><definedNames><definedName name="x">Data!$A$1:$A$8</definedName><definedName name="y">'Other'!$A$9</definedName></definedNames></workbook>"#
+        );
+    }
+}
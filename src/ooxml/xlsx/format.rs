@@ -1,7 +1,14 @@
 //! Shared formatting types for XLSX (used in both reading and writing).
 
+pub use super::data_validation::{
+    DataValidationErrorStyle, DataValidationOperator, DataValidationRule, DataValidationType,
+};
+
 /// Cell format information.
-#[derive(Debug, Clone, Default)]
+///
+/// Build one field by field, with [`CellFormat::builder`], or start from a
+/// preset such as [`CellFormat::bold_header`].
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct CellFormat {
     pub font: Option<CellFont>,
     pub fill: Option<CellFill>,
@@ -10,7 +17,7 @@ pub struct CellFormat {
 }
 
 /// Font properties for a cell.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CellFont {
     pub name: Option<String>,
     pub size: Option<f64>,
@@ -35,7 +42,7 @@ impl Default for CellFont {
 }
 
 /// Fill properties for a cell.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CellFill {
     pub pattern_type: CellFillPatternType,
     pub fg_color: Option<String>,
@@ -43,7 +50,7 @@ pub struct CellFill {
 }
 
 /// Cell fill pattern types.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellFillPatternType {
     None,
     Solid,
@@ -82,7 +89,7 @@ impl CellFillPatternType {
 }
 
 /// Border properties for a cell.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct CellBorder {
     pub left: Option<CellBorderSide>,
     pub right: Option<CellBorderSide>,
@@ -92,14 +99,14 @@ pub struct CellBorder {
 }
 
 /// Border side properties.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct CellBorderSide {
     pub style: CellBorderLineStyle,
     pub color: Option<String>,
 }
 
 /// Border line styles.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellBorderLineStyle {
     None,
     Thin,
@@ -139,6 +146,206 @@ impl CellBorderLineStyle {
     }
 }
 
+impl CellFormat {
+    /// Start building a cell format.
+    pub fn builder() -> CellFormatBuilder {
+        CellFormatBuilder::new()
+    }
+
+    /// Bold text with a thin bottom border, for header rows.
+    pub fn bold_header() -> Self {
+        Self::builder()
+            .bold()
+            .border_bottom(CellBorderLineStyle::Thin, None)
+            .build()
+    }
+
+    /// Two-decimal currency format with thousands separators, e.g. `$1,234.50`.
+    pub fn currency(symbol: &str) -> Self {
+        let symbol = symbol.replace('"', "\"\"");
+        Self::builder()
+            .number_format(&format!("\"{symbol}\"#,##0.00"))
+            .build()
+    }
+
+    /// Two-decimal percentage format, e.g. `12.50%`.
+    pub fn percent() -> Self {
+        Self::builder().number_format("0.00%").build()
+    }
+
+    /// ISO date format (`yyyy-mm-dd`).
+    pub fn date() -> Self {
+        Self::builder().number_format("yyyy-mm-dd").build()
+    }
+}
+
+/// Builder for [`CellFormat`].
+///
+/// Colors may be given as `RRGGBB` or `AARRGGBB` hex; 6-digit colors are
+/// written fully opaque.
+///
+/// # Examples
+///
+/// ```
+/// use litchi::ooxml::xlsx::CellFormat;
+///
+/// let highlight = CellFormat::builder()
+///     .bold()
+///     .fill_color("FFFF00")
+///     .number_format("0.00")
+///     .build();
+/// assert_eq!(highlight.number_format.as_deref(), Some("0.00"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CellFormatBuilder {
+    format: CellFormat,
+}
+
+impl CellFormatBuilder {
+    /// Create a builder for an unformatted cell.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make the text bold.
+    pub fn bold(mut self) -> Self {
+        self.font_mut().bold = true;
+        self
+    }
+
+    /// Make the text italic.
+    pub fn italic(mut self) -> Self {
+        self.font_mut().italic = true;
+        self
+    }
+
+    /// Underline the text.
+    pub fn underline(mut self) -> Self {
+        self.font_mut().underline = true;
+        self
+    }
+
+    /// Set the font name (e.g., "Arial").
+    pub fn font_name(mut self, name: &str) -> Self {
+        self.font_mut().name = Some(name.to_string());
+        self
+    }
+
+    /// Set the font size in points.
+    pub fn font_size(mut self, size: f64) -> Self {
+        self.font_mut().size = Some(size);
+        self
+    }
+
+    /// Set the font color.
+    pub fn font_color(mut self, color: &str) -> Self {
+        self.font_mut().color = Some(argb(color));
+        self
+    }
+
+    /// Replace the whole font.
+    pub fn font(mut self, font: CellFont) -> Self {
+        self.format.font = Some(font);
+        self
+    }
+
+    /// Fill the cell background with a solid color.
+    pub fn fill_color(mut self, color: &str) -> Self {
+        self.format.fill = Some(CellFill {
+            pattern_type: CellFillPatternType::Solid,
+            fg_color: Some(argb(color)),
+            bg_color: None,
+        });
+        self
+    }
+
+    /// Replace the whole fill.
+    pub fn fill(mut self, fill: CellFill) -> Self {
+        self.format.fill = Some(fill);
+        self
+    }
+
+    /// Draw the same border on all four sides.
+    pub fn border_all(self, style: CellBorderLineStyle, color: Option<&str>) -> Self {
+        self.border_top(style, color)
+            .border_bottom(style, color)
+            .border_left(style, color)
+            .border_right(style, color)
+    }
+
+    /// Draw a top border.
+    pub fn border_top(mut self, style: CellBorderLineStyle, color: Option<&str>) -> Self {
+        self.border_mut().top = Some(border_side(style, color));
+        self
+    }
+
+    /// Draw a bottom border.
+    pub fn border_bottom(mut self, style: CellBorderLineStyle, color: Option<&str>) -> Self {
+        self.border_mut().bottom = Some(border_side(style, color));
+        self
+    }
+
+    /// Draw a left border.
+    pub fn border_left(mut self, style: CellBorderLineStyle, color: Option<&str>) -> Self {
+        self.border_mut().left = Some(border_side(style, color));
+        self
+    }
+
+    /// Draw a right border.
+    pub fn border_right(mut self, style: CellBorderLineStyle, color: Option<&str>) -> Self {
+        self.border_mut().right = Some(border_side(style, color));
+        self
+    }
+
+    /// Replace the whole border.
+    pub fn border(mut self, border: CellBorder) -> Self {
+        self.format.border = Some(border);
+        self
+    }
+
+    /// Set the number format code (e.g., "0.00", "yyyy-mm-dd").
+    pub fn number_format(mut self, code: &str) -> Self {
+        self.format.number_format = Some(code.to_string());
+        self
+    }
+
+    /// Finish building the format.
+    pub fn build(self) -> CellFormat {
+        self.format
+    }
+
+    fn font_mut(&mut self) -> &mut CellFont {
+        self.format.font.get_or_insert_with(CellFont::default)
+    }
+
+    fn border_mut(&mut self) -> &mut CellBorder {
+        self.format.border.get_or_insert_with(CellBorder::default)
+    }
+}
+
+impl From<CellFormat> for CellFormatBuilder {
+    fn from(format: CellFormat) -> Self {
+        Self { format }
+    }
+}
+
+fn border_side(style: CellBorderLineStyle, color: Option<&str>) -> CellBorderSide {
+    CellBorderSide {
+        style,
+        color: color.map(argb),
+    }
+}
+
+/// Normalize a hex color to the `AARRGGBB` form used in styles.xml.
+fn argb(color: &str) -> String {
+    let color = color.trim_start_matches('#');
+    if color.len() == 6 {
+        format!("FF{}", color.to_ascii_uppercase())
+    } else {
+        color.to_ascii_uppercase()
+    }
+}
+
 /// Chart types supported in Excel.
 #[derive(Debug, Clone, Copy)]
 pub enum ChartType {
@@ -160,79 +367,6 @@ pub struct Chart {
     pub show_legend: bool,
 }
 
-/// Data validation types.
-#[derive(Debug, Clone)]
-pub enum DataValidationType {
-    Whole {
-        operator: DataValidationOperator,
-        value1: i64,
-        value2: Option<i64>,
-    },
-    Decimal {
-        operator: DataValidationOperator,
-        value1: f64,
-        value2: Option<f64>,
-    },
-    List {
-        values: Vec<String>,
-    },
-    Date {
-        operator: DataValidationOperator,
-        value1: String,
-        value2: Option<String>,
-    },
-    TextLength {
-        operator: DataValidationOperator,
-        value1: i64,
-        value2: Option<i64>,
-    },
-    Custom {
-        formula: String,
-    },
-}
-
-/// Data validation operators.
-#[derive(Debug, Clone, Copy)]
-pub enum DataValidationOperator {
-    Between,
-    NotBetween,
-    Equal,
-    NotEqual,
-    GreaterThan,
-    LessThan,
-    GreaterThanOrEqual,
-    LessThanOrEqual,
-}
-
-impl DataValidationOperator {
-    #[allow(dead_code)]
-    pub(crate) fn as_str(&self) -> &'static str {
-        match self {
-            Self::Between => "between",
-            Self::NotBetween => "notBetween",
-            Self::Equal => "equal",
-            Self::NotEqual => "notEqual",
-            Self::GreaterThan => "greaterThan",
-            Self::LessThan => "lessThan",
-            Self::GreaterThanOrEqual => "greaterThanOrEqual",
-            Self::LessThanOrEqual => "lessThanOrEqual",
-        }
-    }
-}
-
-/// Data validation rule.
-#[derive(Debug, Clone)]
-pub struct DataValidation {
-    pub range: String,
-    pub validation_type: DataValidationType,
-    pub show_input_message: bool,
-    pub input_title: Option<String>,
-    pub input_message: Option<String>,
-    pub show_error_alert: bool,
-    pub error_title: Option<String>,
-    pub error_message: Option<String>,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -352,47 +486,62 @@ mod tests {
     }
 
     #[test]
-    fn test_data_validation_operator_as_str() {
-        assert_eq!(DataValidationOperator::Between.as_str(), "between");
-        assert_eq!(DataValidationOperator::NotBetween.as_str(), "notBetween");
-        assert_eq!(DataValidationOperator::Equal.as_str(), "equal");
-        assert_eq!(DataValidationOperator::NotEqual.as_str(), "notEqual");
-        assert_eq!(DataValidationOperator::GreaterThan.as_str(), "greaterThan");
-        assert_eq!(DataValidationOperator::LessThan.as_str(), "lessThan");
-        assert_eq!(
-            DataValidationOperator::GreaterThanOrEqual.as_str(),
-            "greaterThanOrEqual"
-        );
-        assert_eq!(
-            DataValidationOperator::LessThanOrEqual.as_str(),
-            "lessThanOrEqual"
-        );
+    fn test_cell_format_builder() {
+        let format = CellFormat::builder()
+            .bold()
+            .italic()
+            .font_size(14.0)
+            .font_color("#ff0000")
+            .fill_color("FFFF00")
+            .border_all(CellBorderLineStyle::Thin, Some("000000"))
+            .number_format("0.00")
+            .build();
+
+        let font = format.font.as_ref().unwrap();
+        assert!(font.bold && font.italic && !font.underline);
+        assert_eq!(font.size, Some(14.0));
+        assert_eq!(font.color.as_deref(), Some("FFFF0000"));
+
+        let fill = format.fill.as_ref().unwrap();
+        assert_eq!(fill.pattern_type, CellFillPatternType::Solid);
+        assert_eq!(fill.fg_color.as_deref(), Some("FFFFFF00"));
+
+        let border = format.border.as_ref().unwrap();
+        assert!(border.diagonal.is_none());
+        for side in [&border.left, &border.right, &border.top, &border.bottom] {
+            let side = side.as_ref().unwrap();
+            assert_eq!(side.style, CellBorderLineStyle::Thin);
+            assert_eq!(side.color.as_deref(), Some("FF000000"));
+        }
+        assert_eq!(format.number_format.as_deref(), Some("0.00"));
     }
 
     #[test]
-    fn test_data_validation_types() {
-        let whole = DataValidationType::Whole {
-            operator: DataValidationOperator::Between,
-            value1: 1,
-            value2: Some(10),
-        };
-        assert!(matches!(whole, DataValidationType::Whole { .. }));
-
-        let decimal = DataValidationType::Decimal {
-            operator: DataValidationOperator::GreaterThan,
-            value1: 0.0,
-            value2: None,
-        };
-        assert!(matches!(decimal, DataValidationType::Decimal { .. }));
+    fn test_cell_format_presets() {
+        let header = CellFormat::bold_header();
+        assert!(header.font.unwrap().bold);
+        assert!(header.border.unwrap().bottom.is_some());
 
-        let list = DataValidationType::List {
-            values: vec!["A".to_string(), "B".to_string()],
-        };
-        assert!(matches!(list, DataValidationType::List { .. }));
+        assert_eq!(
+            CellFormat::currency("$").number_format.as_deref(),
+            Some("\"$\"#,##0.00")
+        );
+        assert_eq!(
+            CellFormat::percent().number_format.as_deref(),
+            Some("0.00%")
+        );
+        assert_eq!(
+            CellFormat::date().number_format.as_deref(),
+            Some("yyyy-mm-dd")
+        );
 
-        let custom = DataValidationType::Custom {
-            formula: "A1>0".to_string(),
-        };
-        assert!(matches!(custom, DataValidationType::Custom { .. }));
+        let bold_currency = CellFormatBuilder::from(CellFormat::currency("€"))
+            .bold()
+            .build();
+        assert!(bold_currency.font.unwrap().bold);
+        assert_eq!(
+            bold_currency.number_format.as_deref(),
+            Some("\"€\"#,##0.00")
+        );
     }
 }
@@ -9,6 +9,7 @@
 //!
 //! - `Workbook`: The main workbook content and API
 //! - `Worksheet`: Individual sheet content and data access
+//! - `WorkbookEditor`: Targeted cell and row/column edits to an existing file
 //! - Various internal parsers for styles, shared strings, etc.
 //!
 //! # Example
@@ -29,9 +30,15 @@
 
 pub mod cell;
 pub mod chart;
+pub mod comments;
+pub mod conditional_format;
+pub mod data_validation;
+pub mod editor;
 pub mod format;
+pub mod page_layout;
 pub mod parsers;
 pub mod pivot;
+pub mod rows;
 pub mod shared_strings;
 pub mod sort;
 pub mod sparkline;
@@ -44,14 +51,24 @@ pub mod workbook;
 pub mod worksheet;
 pub mod writer;
 
+mod references;
+
 // Re-export main types for convenience
 pub use cell::Cell;
 pub use chart::{ChartAnchor, WorksheetChart};
+pub use comments::{CommentThread, ThreadedCommentEntry, WorksheetComment};
+pub use conditional_format::{Cfvo, CfvoType, ColorScaleStop, IconSetStyle};
 // Re-export shared formatting types
+pub use data_validation::{
+    DataValidationErrorStyle, DataValidationOperator, DataValidationRule, DataValidationType,
+};
+pub use editor::{EditableWorksheet, WorkbookEditor};
 pub use format::{
     CellBorder, CellBorderLineStyle, CellBorderSide, CellFill, CellFillPatternType, CellFont,
-    CellFormat, DataValidation, DataValidationOperator, DataValidationType,
+    CellFormat, CellFormatBuilder,
 };
+pub use page_layout::{PageMargins, PrintOptions};
+pub use rows::RowsIter;
 pub use shared_strings::SharedStrings;
 pub use sort::{SortBy, SortCondition, SortMethod, SortState};
 pub use sparkline::{
@@ -60,19 +77,21 @@ pub use sparkline::{
 };
 pub use styles::{Alignment, Border, BorderStyle, CellStyle, Fill, Font, NumberFormat, Styles};
 pub use table::{Table, TableColumn, TableFormula, TableStyleInfo, TableType, TotalsRowFunction};
-pub use views::{SheetView, SheetViewType};
+pub use views::{Pane, PaneState, PaneType, Selection, SheetView, SheetViewType};
 pub use workbook::Workbook;
 pub use worksheet::{
-    AutoFilter, ColumnInfo, Comment, ConditionalFormatRule, DataValidationRule, Hyperlink,
-    PageBreak, PageSetup, RowInfo, Worksheet, WorksheetInfo,
+    AutoFilter, ColumnInfo, Comment, ConditionalFormatRule, Hyperlink, PageBreak, PageSetup,
+    RowInfo, Worksheet, WorksheetInfo,
 };
 // Re-export pivot types
 pub use pivot::{
     AxisType, DataField, FieldItem, ItemType, Location, PageField, PivotArea, PivotCacheDefinition,
     PivotCacheField, PivotCacheRecords, PivotField, PivotFilter, PivotTableDefinition,
-    PivotTableStyle, Reference, RowColField, RowColItem, SharedItem, SortType, Subtotal,
-    read_pivot_cache_definition, read_pivot_table_definition, read_pivot_tables,
-    write_pivot_cache_definition, write_pivot_cache_records, write_pivot_table,
+    PivotTableStyle, PivotTableView, PivotViewDataField, PivotViewField, PivotViewItem,
+    PivotViewPageField, Reference, RowColField, RowColItem, SharedItem, SortType, Subtotal,
+    read_pivot_cache_definition, read_pivot_cache_records, read_pivot_table_definition,
+    read_pivot_table_views, read_pivot_tables, write_pivot_cache_definition,
+    write_pivot_cache_records, write_pivot_table,
 };
 // Re-export writer types
 pub use writer::{
@@ -0,0 +1,185 @@
+//! Page margins and print options for XLSX worksheets (used in both reading
+//! and writing).
+//!
+//! These map to the `<pageMargins>` and `<printOptions>` elements of the
+//! worksheet XML. Page size, orientation and scaling live in `<pageSetup>`,
+//! and print titles are stored as the `_xlnm.Print_Titles` defined name in
+//! workbook.xml.
+
+use std::fmt::Write as FmtWrite;
+
+use crate::sheet::Result as SheetResult;
+
+/// Page margins in inches.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageMargins {
+    /// Left margin
+    pub left: f64,
+    /// Right margin
+    pub right: f64,
+    /// Top margin
+    pub top: f64,
+    /// Bottom margin
+    pub bottom: f64,
+    /// Header margin, from the top edge of the page
+    pub header: f64,
+    /// Footer margin, from the bottom edge of the page
+    pub footer: f64,
+}
+
+impl Default for PageMargins {
+    /// Excel's "Normal" margins.
+    fn default() -> Self {
+        Self {
+            left: 0.7,
+            right: 0.7,
+            top: 0.75,
+            bottom: 0.75,
+            header: 0.3,
+            footer: 0.3,
+        }
+    }
+}
+
+impl PageMargins {
+    /// Parse a `<pageMargins>` tag. Missing margins keep their defaults.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let defaults = Self::default();
+        let margin = |name: &str, default: f64| {
+            extract_attribute(tag, name)
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(default)
+        };
+
+        Some(Self {
+            left: margin("left", defaults.left),
+            right: margin("right", defaults.right),
+            top: margin("top", defaults.top),
+            bottom: margin("bottom", defaults.bottom),
+            header: margin("header", defaults.header),
+            footer: margin("footer", defaults.footer),
+        })
+    }
+
+    /// Write the `<pageMargins>` element.
+    pub fn write(&self, xml: &mut String) -> SheetResult<()> {
+        write!(
+            xml,
+            r#"<pageMargins left="{}" right="{}" top="{}" bottom="{}" header="{}" footer="{}"/>"#,
+            self.left, self.right, self.top, self.bottom, self.header, self.footer
+        )
+        .map_err(|e| format!("XML write error: {}", e))?;
+        Ok(())
+    }
+}
+
+/// Print options of a worksheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PrintOptions {
+    /// Center the printed area horizontally on the page
+    pub horizontal_centered: bool,
+    /// Center the printed area vertically on the page
+    pub vertical_centered: bool,
+    /// Print row and column headings
+    pub headings: bool,
+    /// Print grid lines
+    pub grid_lines: bool,
+}
+
+impl PrintOptions {
+    /// Parse a `<printOptions>` tag.
+    pub fn parse(tag: &str) -> Option<Self> {
+        let flag = |name: &str| {
+            extract_attribute(tag, name).is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        };
+
+        Some(Self {
+            horizontal_centered: flag("horizontalCentered"),
+            vertical_centered: flag("verticalCentered"),
+            headings: flag("headings"),
+            grid_lines: flag("gridLines"),
+        })
+    }
+
+    /// Whether every option is off, in which case nothing needs writing.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Write the `<printOptions>` element, omitting options that are off.
+    pub fn write(&self, xml: &mut String) -> SheetResult<()> {
+        xml.push_str("<printOptions");
+        if self.horizontal_centered {
+            xml.push_str(r#" horizontalCentered="1""#);
+        }
+        if self.vertical_centered {
+            xml.push_str(r#" verticalCentered="1""#);
+        }
+        if self.headings {
+            xml.push_str(r#" headings="1""#);
+        }
+        if self.grid_lines {
+            xml.push_str(r#" gridLines="1""#);
+        }
+        xml.push_str("/>");
+        Ok(())
+    }
+}
+
+fn extract_attribute(tag: &str, attr: &str) -> Option<String> {
+    let search_str = format!(" {}=\"", attr);
+    let start = tag.find(&search_str)? + search_str.len();
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn page_margins_round_trip() {
+        let margins = PageMargins {
+            left: 0.25,
+            right: 0.25,
+            top: 1.0,
+            bottom: 0.5,
+            header: 0.1,
+            footer: 0.2,
+        };
+        let mut xml = String::new();
+        margins.write(&mut xml).unwrap();
+        assert_eq!(
+            xml,
+            r#"<pageMargins left="0.25" right="0.25" top="1" bottom="0.5" header="0.1" footer="0.2"/>"#
+        );
+        assert_eq!(PageMargins::parse(&xml), Some(margins));
+    }
+
+    #[test]
+    fn page_margins_parse_fills_in_defaults() {
+        let margins = PageMargins::parse(r#"<pageMargins left="1" right="1"/>"#).unwrap();
+        assert_eq!(margins.left, 1.0);
+        assert_eq!(margins.top, PageMargins::default().top);
+    }
+
+    #[test]
+    fn print_options_round_trip() {
+        let options = PrintOptions {
+            horizontal_centered: true,
+            grid_lines: true,
+            ..Default::default()
+        };
+        let mut xml = String::new();
+        options.write(&mut xml).unwrap();
+        assert_eq!(
+            xml,
+            r#"<printOptions horizontalCentered="1" gridLines="1"/>"#
+        );
+        assert_eq!(PrintOptions::parse(&xml), Some(options));
+
+        // gridLinesSet must not be mistaken for gridLines
+        let parsed = PrintOptions::parse(r#"<printOptions gridLinesSet="1"/>"#).unwrap();
+        assert!(parsed.is_default());
+    }
+}
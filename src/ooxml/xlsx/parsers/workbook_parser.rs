@@ -8,6 +8,7 @@
 //! - Uses atoi_simd for fast integer parsing
 //! - Pre-allocates vectors with reasonable capacities
 
+use crate::common::{Protection, ProtectionKind};
 use crate::ooxml::xlsx::worksheet::WorksheetInfo;
 use crate::sheet::Result;
 
@@ -145,8 +146,9 @@ pub fn parse_sheet_xml(sheet_xml: &str) -> Result<Option<WorksheetInfo>> {
 }
 
 fn extract_date1904_flag(fragment: &str) -> bool {
-    if let Some(attr_start) = memchr::memmem::find(fragment.as_bytes(), b"date1904=\"") {
-        let value_start = attr_start + 9;
+    const ATTR: &[u8] = b"date1904=\"";
+    if let Some(attr_start) = memchr::memmem::find(fragment.as_bytes(), ATTR) {
+        let value_start = attr_start + ATTR.len();
         let bytes = fragment.as_bytes();
         if value_start < bytes.len()
             && let Some(quote_end_rel) = memchr::memchr(b'"', &bytes[value_start..])
@@ -158,3 +160,59 @@ fn extract_date1904_flag(fragment: &str) -> bool {
 
     false
 }
+
+/// Parse the protection elements of workbook.xml or of a worksheet part.
+///
+/// `fileSharing` carries the read-only recommendation and the password to
+/// modify, `workbookProtection` the structure and revision locks, and
+/// `sheetProtection` the lock of a worksheet. A locked structure, window
+/// layout or sheet makes the file read-only.
+pub fn parse_protection(content: &str) -> Protection {
+    use quick_xml::Reader;
+    use quick_xml::events::Event;
+
+    let is_true = |value: &[u8]| matches!(value, b"1" | b"true");
+    let mut protection = Protection::default();
+    for tag in ["<fileSharing", "<workbookProtection", "<sheetProtection"] {
+        let Some(start) = memchr::memmem::find(content.as_bytes(), tag.as_bytes()) else {
+            continue;
+        };
+        let mut reader = Reader::from_str(&content[start..]);
+        let (Ok(Event::Empty(e)) | Ok(Event::Start(e))) = reader.read_event() else {
+            continue;
+        };
+
+        let mut kind = ProtectionKind::None;
+        let mut has_password = false;
+        for attr in e.attributes().flatten() {
+            let value = attr.value.as_ref();
+            match attr.key.local_name().as_ref() {
+                b"readOnlyRecommended" if is_true(value) => {
+                    kind = kind.max(ProtectionKind::ReadOnlyRecommended);
+                },
+                b"lockRevision" if is_true(value) => {
+                    kind = kind.max(ProtectionKind::TrackedChanges);
+                },
+                b"lockStructure" | b"lockWindows" | b"sheet" if is_true(value) => {
+                    kind = kind.max(ProtectionKind::ReadOnly);
+                },
+                // A password to modify makes the file read-only without it
+                b"reservationPassword" | b"hashValue" if tag == "<fileSharing" => {
+                    kind = kind.max(ProtectionKind::ReadOnly);
+                    has_password = true;
+                },
+                b"workbookPassword"
+                | b"workbookHashValue"
+                | b"revisionsPassword"
+                | b"revisionsHashValue"
+                | b"password"
+                | b"hashValue" => has_password = true,
+                _ => {},
+            }
+        }
+        if kind != ProtectionKind::None {
+            protection = protection.or(Protection { kind, has_password });
+        }
+    }
+    protection
+}
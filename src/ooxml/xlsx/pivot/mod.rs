@@ -3,13 +3,20 @@ pub mod fields;
 pub mod filters;
 pub mod reader;
 pub mod styles;
+pub mod view;
 pub mod writer;
 
 pub use cache::{PivotCacheDefinition, PivotCacheField, PivotCacheRecords, SharedItem};
 pub use fields::{DataField, FieldItem, PageField, PivotField, RowColField, RowColItem, Subtotal};
 pub use filters::{PivotArea, PivotFilter, Reference};
-pub use reader::{read_pivot_cache_definition, read_pivot_table_definition, read_pivot_tables};
+pub use reader::{
+    read_pivot_cache_definition, read_pivot_cache_records, read_pivot_table_definition,
+    read_pivot_table_views, read_pivot_tables,
+};
 pub use styles::{Location, PivotTableStyle};
+pub use view::{
+    PivotTableView, PivotViewDataField, PivotViewField, PivotViewItem, PivotViewPageField,
+};
 pub use writer::{
     PivotTableDefinition, write_pivot_cache_definition, write_pivot_cache_records,
     write_pivot_table,
@@ -1,3 +1,6 @@
+use std::collections::HashMap;
+
+use crate::common::xml::attr;
 use crate::ooxml::opc::constants::relationship_type as rt;
 use crate::ooxml::opc::{OpcPackage, PackURI};
 use crate::ooxml::pivot::{
@@ -6,12 +9,18 @@ use crate::ooxml::pivot::{
 use crate::ooxml::xlsx::parsers::workbook_parser;
 use crate::sheet::Result as SheetResult;
 
-use super::cache::{PivotCacheDefinition, PivotCacheField, SharedItem};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+
+use super::cache::{
+    CacheRecord, PivotCacheDefinition, PivotCacheField, PivotCacheRecords, SharedItem,
+};
+use super::view::PivotTableView;
 
 pub fn read_pivot_tables(package: &OpcPackage) -> SheetResult<Vec<PivotTable>> {
     let workbook_uri = PackURI::new("/xl/workbook.xml")?;
     let workbook_part = package.get_part(&workbook_uri)?;
-    let workbook_xml = std::str::from_utf8(workbook_part.blob())?;
+    let workbook_xml = std::str::from_utf8(workbook_part.try_blob()?)?;
 
     let (worksheets, _, _) = workbook_parser::parse_workbook_xml(workbook_xml)?;
 
@@ -39,7 +48,7 @@ pub fn read_pivot_tables(package: &OpcPackage) -> SheetResult<Vec<PivotTable>> {
 
             let table_uri = rel.target_partname()?;
             let table_part = package.get_part(&table_uri)?;
-            let xml = std::str::from_utf8(table_part.blob())?;
+            let xml = std::str::from_utf8(table_part.try_blob()?)?;
 
             if let Some(table) = parse_pivot_table_definition(xml, &ws_info.name)? {
                 tables.push(table);
@@ -50,6 +59,85 @@ pub fn read_pivot_tables(package: &OpcPackage) -> SheetResult<Vec<PivotTable>> {
     Ok(tables)
 }
 
+/// Read every pivot table in the package, resolved against its pivot cache.
+///
+/// Cache definitions and records are read once per cache and shared by the
+/// pivot tables that use them.
+pub fn read_pivot_table_views(package: &OpcPackage) -> SheetResult<Vec<PivotTableView>> {
+    let workbook_uri = PackURI::new("/xl/workbook.xml")?;
+    let workbook_part = package.get_part(&workbook_uri)?;
+    let workbook_xml = std::str::from_utf8(workbook_part.try_blob()?)?;
+
+    let (worksheets, _, _) = workbook_parser::parse_workbook_xml(workbook_xml)?;
+
+    let workbook_rels = workbook_part.rels();
+    let mut caches: HashMap<String, (PivotCacheDefinition, PivotCacheRecords)> = HashMap::new();
+    let mut views = Vec::new();
+
+    for ws_info in worksheets {
+        let rel = match workbook_rels.get(ws_info.relationship_id.as_str()) {
+            Some(r) => r,
+            None => continue,
+        };
+
+        let sheet_uri = rel.target_partname()?;
+        let sheet_part = package.get_part(&sheet_uri)?;
+
+        for rel in sheet_part.rels().iter() {
+            if rel.reltype() != rt::PIVOT_TABLE {
+                continue;
+            }
+
+            let table_uri = rel.target_partname()?;
+            let table_part = package.get_part(&table_uri)?;
+            let table_xml = std::str::from_utf8(table_part.try_blob()?)?;
+
+            let cache_rel = match table_part
+                .rels()
+                .iter()
+                .find(|r| r.reltype() == rt::PIVOT_CACHE_DEFINITION)
+            {
+                Some(r) => r,
+                None => continue,
+            };
+            let cache_uri = cache_rel.target_partname()?;
+
+            if !caches.contains_key(cache_uri.as_str()) {
+                let cache_part = package.get_part(&cache_uri)?;
+                let cache_xml = std::str::from_utf8(cache_part.try_blob()?)?;
+                let cache_def = match read_pivot_cache_definition(cache_xml)? {
+                    Some(def) => def,
+                    None => continue,
+                };
+
+                let records = match cache_part
+                    .rels()
+                    .iter()
+                    .find(|r| r.reltype() == rt::PIVOT_CACHE_RECORDS)
+                {
+                    Some(records_rel) => {
+                        let records_part = package.get_part(&records_rel.target_partname()?)?;
+                        let records_xml = std::str::from_utf8(records_part.try_blob()?)?;
+                        read_pivot_cache_records(records_xml, &cache_def)?
+                    },
+                    None => PivotCacheRecords::default(),
+                };
+
+                caches.insert(cache_uri.as_str().to_string(), (cache_def, records));
+            }
+
+            let (cache_def, records) = &caches[cache_uri.as_str()];
+            if let Some(view) =
+                PivotTableView::from_xml(table_xml, &ws_info.name, cache_def, records)?
+            {
+                views.push(view);
+            }
+        }
+    }
+
+    Ok(views)
+}
+
 fn parse_pivot_table_definition(xml: &str, sheet_name: &str) -> SheetResult<Option<PivotTable>> {
     let (name, cache_id) = match extract_pivot_table_root_attrs(xml) {
         Some(v) => v,
@@ -148,7 +236,7 @@ fn parse_cache_fields(xml: &str) -> Vec<PivotCacheField> {
     let section = &xml[start..start + end_rel];
     let mut pos = 0;
 
-    while let Some(rel) = section[pos..].find("<cacheField") {
+    while let Some(rel) = section[pos..].find("<cacheField ") {
         let field_start = pos + rel;
         let field_after = &section[field_start..];
 
@@ -194,89 +282,131 @@ fn parse_cache_field(xml: &str) -> Option<PivotCacheField> {
 }
 
 fn parse_shared_items(xml: &str) -> Vec<SharedItem> {
+    let mut reader = Reader::from_str(xml);
     let mut items = Vec::new();
-
-    let start = match xml.find("<sharedItems") {
-        Some(s) => s,
-        None => return items,
-    };
-
-    let end = match xml[start..].find("</sharedItems>") {
-        Some(e) => start + e,
-        None => return items,
-    };
-
-    let section = &xml[start..end];
-
-    let mut pos = 0;
-    while pos < section.len() {
-        if let Some(m_pos) = section[pos..].find("<m") {
-            items.push(SharedItem::Missing);
-            pos += m_pos + 1;
-        } else if let Some(n_pos) = section[pos..].find("<n ") {
-            let n_start = pos + n_pos;
-            if let Some(n_end) = section[n_start..].find("/>") {
-                let n_tag = &section[n_start..n_start + n_end + 2];
-                if let Some(v_str) = extract_attr(n_tag, "v")
-                    && let Ok(v) = v_str.parse::<f64>()
-                {
-                    items.push(SharedItem::Number(v));
+    let mut in_shared_items = false;
+    let mut depth = 0usize;
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) => {
+                if in_shared_items {
+                    depth += 1;
+                    // Items can carry child elements (e.g. tuples)
+                    if depth == 1
+                        && let Some(item) = shared_item_from_element(e)
+                    {
+                        items.push(item);
+                    }
+                } else if e.local_name().as_ref() == b"sharedItems" {
+                    in_shared_items = true;
                 }
-                pos = n_start + n_end + 2;
-            } else {
-                break;
-            }
-        } else if let Some(s_pos) = section[pos..].find("<s ") {
-            let s_start = pos + s_pos;
-            if let Some(s_end) = section[s_start..].find("/>") {
-                let s_tag = &section[s_start..s_start + s_end + 2];
-                if let Some(v) = extract_attr(s_tag, "v") {
-                    items.push(SharedItem::String(v));
+            },
+            Ok(Event::Empty(ref e)) => {
+                if in_shared_items
+                    && depth == 0
+                    && let Some(item) = shared_item_from_element(e)
+                {
+                    items.push(item);
                 }
-                pos = s_start + s_end + 2;
-            } else {
-                break;
-            }
-        } else if let Some(b_pos) = section[pos..].find("<b ") {
-            let b_start = pos + b_pos;
-            if let Some(b_end) = section[b_start..].find("/>") {
-                let b_tag = &section[b_start..b_start + b_end + 2];
-                if let Some(v_str) = extract_attr(b_tag, "v") {
-                    let v = v_str == "1" || v_str.to_lowercase() == "true";
-                    items.push(SharedItem::Boolean(v));
+            },
+            Ok(Event::End(_)) if in_shared_items => {
+                if depth == 0 {
+                    break;
                 }
-                pos = b_start + b_end + 2;
-            } else {
-                break;
-            }
-        } else if let Some(e_pos) = section[pos..].find("<e ") {
-            let e_start = pos + e_pos;
-            if let Some(e_end) = section[e_start..].find("/>") {
-                let e_tag = &section[e_start..e_start + e_end + 2];
-                if let Some(v) = extract_attr(e_tag, "v") {
-                    items.push(SharedItem::Error(v));
+                depth -= 1;
+            },
+            Ok(Event::Eof) | Err(_) => break,
+            _ => {},
+        }
+    }
+
+    items
+}
+
+/// Read a `pivotCacheRecords` part.
+///
+/// Record values stored as indexes (`<x v="..."/>`) are resolved against the
+/// shared items of the matching cache field.
+pub fn read_pivot_cache_records(
+    xml: &str,
+    cache_def: &PivotCacheDefinition,
+) -> SheetResult<PivotCacheRecords> {
+    let mut reader = Reader::from_str(xml);
+    let mut records = PivotCacheRecords::default();
+    let mut current: Option<CacheRecord> = None;
+    let mut depth = 0usize;
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Failed to parse pivot cache records: {}", e))?;
+        match event {
+            Event::Start(ref e) | Event::Empty(ref e) => {
+                let is_empty = matches!(event, Event::Empty(_));
+                match current.as_mut() {
+                    None if e.local_name().as_ref() == b"r" => {
+                        let record = CacheRecord::default();
+                        if is_empty {
+                            records.records.push(record);
+                        } else {
+                            current = Some(record);
+                        }
+                    },
+                    Some(record) => {
+                        if depth == 0 {
+                            let field = record.values.len();
+                            let value = if e.local_name().as_ref() == b"x" {
+                                attr(e, b"v")
+                                    .and_then(|v| v.parse::<usize>().ok())
+                                    .and_then(|idx| {
+                                        cache_def
+                                            .cache_fields
+                                            .get(field)?
+                                            .shared_items
+                                            .get(idx)
+                                            .cloned()
+                                    })
+                                    .unwrap_or(SharedItem::Missing)
+                            } else {
+                                shared_item_from_element(e).unwrap_or(SharedItem::Missing)
+                            };
+                            record.values.push(value);
+                        }
+                        if !is_empty {
+                            depth += 1;
+                        }
+                    },
+                    None => {},
                 }
-                pos = e_start + e_end + 2;
-            } else {
-                break;
-            }
-        } else if let Some(d_pos) = section[pos..].find("<d ") {
-            let d_start = pos + d_pos;
-            if let Some(d_end) = section[d_start..].find("/>") {
-                let d_tag = &section[d_start..d_start + d_end + 2];
-                if let Some(v) = extract_attr(d_tag, "v") {
-                    items.push(SharedItem::DateTime(v));
+            },
+            Event::End(_) => {
+                if depth > 0 {
+                    depth -= 1;
+                } else if let Some(record) = current.take() {
+                    records.records.push(record);
                 }
-                pos = d_start + d_end + 2;
-            } else {
-                break;
-            }
-        } else {
-            break;
+            },
+            Event::Eof => break,
+            _ => {},
         }
     }
 
-    items
+    Ok(records)
+}
+
+/// Convert a shared item element (`<s>`, `<n>`, `<b>`, `<e>`, `<d>`, `<m>`) to a value.
+fn shared_item_from_element(e: &BytesStart<'_>) -> Option<SharedItem> {
+    let value = attr(e, b"v");
+    match e.local_name().as_ref() {
+        b"m" => Some(SharedItem::Missing),
+        b"n" => value.and_then(|v| v.parse().ok()).map(SharedItem::Number),
+        b"b" => value.map(|v| SharedItem::Boolean(v == "1" || v.eq_ignore_ascii_case("true"))),
+        b"e" => value.map(SharedItem::Error),
+        b"s" => value.map(SharedItem::String),
+        b"d" => value.map(SharedItem::DateTime),
+        _ => None,
+    }
 }
 
 fn extract_pivot_table_root_attrs(xml: &str) -> Option<(String, u32)> {
@@ -0,0 +1,810 @@
+//! Resolved view of a pivot table.
+//!
+//! A pivotTableDefinition part refers to everything by index: fields are
+//! indexes into the cache fields, field items are indexes into the shared
+//! items of a cache field, and page field selections are indexes into the
+//! field items. [`PivotTableView`] resolves those indexes against the pivot
+//! cache so fields and items can be used by name and value.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use crate::common::xml::attr;
+use crate::sheet::{CellValue, Result as SheetResult};
+
+use super::AxisType;
+use super::cache::{PivotCacheDefinition, PivotCacheRecords, SharedItem};
+use super::fields::Subtotal;
+
+/// A pivot table with field and item indexes resolved against its cache.
+#[derive(Debug, Clone)]
+pub struct PivotTableView {
+    /// Pivot table name
+    pub name: String,
+    /// Name of the sheet the pivot table is placed on
+    pub sheet_name: String,
+    /// Range occupied by the pivot table (e.g., "A3:C10")
+    pub location_ref: String,
+    /// All fields, in cache field order
+    pub fields: Vec<PivotViewField>,
+    /// Indexes into `fields` of the row fields, outermost first
+    pub row_fields: Vec<usize>,
+    /// Indexes into `fields` of the column fields, outermost first
+    pub column_fields: Vec<usize>,
+    /// Report filter fields
+    pub page_fields: Vec<PivotViewPageField>,
+    /// Value fields
+    pub data_fields: Vec<PivotViewDataField>,
+    /// Cache records, one value per field
+    pub records: Vec<Vec<SharedItem>>,
+}
+
+/// A pivot field resolved against its cache field.
+#[derive(Debug, Clone)]
+pub struct PivotViewField {
+    /// Index of the field in the cache
+    pub index: usize,
+    /// Cache field name
+    pub name: String,
+    /// Custom caption shown in the pivot table, if any
+    pub caption: Option<String>,
+    /// Axis the field is placed on
+    pub axis: Option<AxisType>,
+    /// Items of the field, in pivot table order
+    pub items: Vec<PivotViewItem>,
+}
+
+impl PivotViewField {
+    /// The name shown in the pivot table.
+    pub fn display_name(&self) -> &str {
+        self.caption.as_deref().unwrap_or(&self.name)
+    }
+
+    fn is_hidden(&self, value: &SharedItem) -> bool {
+        self.items
+            .iter()
+            .any(|item| item.hidden && item.value == *value)
+    }
+}
+
+/// A field item resolved to its shared item value.
+#[derive(Debug, Clone)]
+pub struct PivotViewItem {
+    /// Item value
+    pub value: SharedItem,
+    /// Custom caption shown in the pivot table, if any
+    pub caption: Option<String>,
+    /// Whether the item is filtered out
+    pub hidden: bool,
+}
+
+/// A report filter field.
+#[derive(Debug, Clone)]
+pub struct PivotViewPageField {
+    /// Index into [`PivotTableView::fields`]
+    pub field: usize,
+    /// Selected item, or `None` when all items are shown
+    pub selected: Option<SharedItem>,
+}
+
+/// A value field with its aggregation.
+#[derive(Debug, Clone)]
+pub struct PivotViewDataField {
+    /// Name shown in the pivot table (e.g., "Sum of Amount")
+    pub name: String,
+    /// Index into [`PivotTableView::fields`] of the aggregated field
+    pub field: usize,
+    /// Aggregation function
+    pub subtotal: Subtotal,
+}
+
+impl PivotTableView {
+    /// Resolve a pivotTableDefinition part against its cache.
+    ///
+    /// Returns `Ok(None)` if the XML has no pivotTableDefinition element.
+    pub fn from_xml(
+        xml: &str,
+        sheet_name: &str,
+        cache_def: &PivotCacheDefinition,
+        records: &PivotCacheRecords,
+    ) -> SheetResult<Option<Self>> {
+        let definition = match RawDefinition::parse(xml)? {
+            Some(def) => def,
+            None => return Ok(None),
+        };
+
+        let mut fields: Vec<PivotViewField> = cache_def
+            .cache_fields
+            .iter()
+            .enumerate()
+            .map(|(index, cache_field)| PivotViewField {
+                index,
+                name: cache_field.name.clone(),
+                caption: cache_field.caption.clone(),
+                axis: None,
+                items: Vec::new(),
+            })
+            .collect();
+
+        // Item indexes of each field's <item> elements, kept for page field selections
+        let mut item_indexes: Vec<Vec<Option<usize>>> = vec![Vec::new(); fields.len()];
+
+        for (index, raw) in definition.pivot_fields.iter().enumerate() {
+            let Some(field) = fields.get_mut(index) else {
+                break;
+            };
+            if raw.name.is_some() {
+                field.caption = raw.name.clone();
+            }
+            field.axis = raw.axis.clone();
+
+            let shared_items = &cache_def.cache_fields[index].shared_items;
+            for raw_item in &raw.items {
+                // Subtotal and grand total items have a type and no value
+                let value = raw_item
+                    .x
+                    .filter(|_| raw_item.item_type.is_none())
+                    .and_then(|x| shared_items.get(x));
+                match value {
+                    Some(value) => {
+                        item_indexes[index].push(Some(field.items.len()));
+                        field.items.push(PivotViewItem {
+                            value: value.clone(),
+                            caption: raw_item.name.clone(),
+                            hidden: raw_item.hidden,
+                        });
+                    },
+                    None => item_indexes[index].push(None),
+                }
+            }
+        }
+
+        let field_count = fields.len();
+        let in_range = |idx: &i64| (0..field_count as i64).contains(idx);
+        // Index -2 is the "Values" pseudo-field that places data fields on an axis
+        let row_fields = definition
+            .row_fields
+            .iter()
+            .filter(|idx| in_range(idx))
+            .map(|&idx| idx as usize)
+            .collect();
+        let column_fields = definition
+            .column_fields
+            .iter()
+            .filter(|idx| in_range(idx))
+            .map(|&idx| idx as usize)
+            .collect();
+
+        let page_fields = definition
+            .page_fields
+            .iter()
+            .filter(|(fld, _)| *fld < field_count)
+            .map(|&(field, item)| PivotViewPageField {
+                field,
+                selected: item
+                    .and_then(|item| item_indexes[field].get(item).copied().flatten())
+                    .map(|pos| fields[field].items[pos].value.clone()),
+            })
+            .collect();
+
+        let data_fields = definition
+            .data_fields
+            .iter()
+            .filter(|raw| raw.field < field_count)
+            .map(|raw| PivotViewDataField {
+                name: raw.name.clone().unwrap_or_else(|| {
+                    format!(
+                        "{} of {}",
+                        subtotal_label(raw.subtotal),
+                        fields[raw.field].display_name()
+                    )
+                }),
+                field: raw.field,
+                subtotal: raw.subtotal,
+            })
+            .collect();
+
+        Ok(Some(Self {
+            name: definition.name,
+            sheet_name: sheet_name.to_string(),
+            location_ref: definition.location_ref,
+            fields,
+            row_fields,
+            column_fields,
+            page_fields,
+            data_fields,
+            records: records
+                .records
+                .iter()
+                .map(|record| record.values.clone())
+                .collect(),
+        }))
+    }
+
+    /// Get a field by cache index.
+    pub fn field(&self, index: usize) -> Option<&PivotViewField> {
+        self.fields.get(index)
+    }
+
+    /// Get a field by cache field name.
+    pub fn field_by_name(&self, name: &str) -> Option<&PivotViewField> {
+        self.fields.iter().find(|f| f.name == name)
+    }
+
+    /// Row fields, outermost first.
+    pub fn row_fields(&self) -> impl Iterator<Item = &PivotViewField> {
+        self.row_fields.iter().map(|&idx| &self.fields[idx])
+    }
+
+    /// Column fields, outermost first.
+    pub fn column_fields(&self) -> impl Iterator<Item = &PivotViewField> {
+        self.column_fields.iter().map(|&idx| &self.fields[idx])
+    }
+
+    /// Report filter fields.
+    pub fn page_fields(&self) -> impl Iterator<Item = (&PivotViewField, Option<&SharedItem>)> {
+        self.page_fields
+            .iter()
+            .map(|pf| (&self.fields[pf.field], pf.selected.as_ref()))
+    }
+
+    /// Records that pass the report filters and hidden items.
+    pub fn visible_records(&self) -> impl Iterator<Item = &[SharedItem]> {
+        let axis_fields: Vec<&PivotViewField> = self
+            .row_fields()
+            .chain(self.column_fields())
+            .chain(self.page_fields().map(|(field, _)| field))
+            .collect();
+
+        self.records
+            .iter()
+            .map(Vec::as_slice)
+            .filter(move |record| {
+                let selected = self.page_fields.iter().all(|pf| match &pf.selected {
+                    Some(selected) => record.get(pf.field) == Some(selected),
+                    None => true,
+                });
+                selected
+                    && axis_fields
+                        .iter()
+                        .all(|field| match record.get(field.index) {
+                            Some(value) => !field.is_hidden(value),
+                            None => true,
+                        })
+            })
+    }
+
+    /// Compute the pivot table as a flat grid, without subtotals or grand totals.
+    ///
+    /// The first row holds headers: the row field names followed by one column
+    /// per column item combination and value field. Each following row holds
+    /// one combination of row items followed by the aggregated values. Items
+    /// are ordered as in the pivot table, with values missing from the field
+    /// items appended in record order.
+    pub fn to_grid(&self) -> Vec<Vec<CellValue>> {
+        let records: Vec<&[SharedItem]> = self.visible_records().collect();
+
+        let row_orders: Vec<ItemOrder> = self
+            .row_fields()
+            .map(|field| ItemOrder::new(field, &records))
+            .collect();
+        let col_orders: Vec<ItemOrder> = self
+            .column_fields()
+            .map(|field| ItemOrder::new(field, &records))
+            .collect();
+
+        // (row key, column key) -> values per data field
+        let mut groups: BTreeMap<(Vec<usize>, Vec<usize>), GroupValues<'_>> = BTreeMap::new();
+        let mut col_keys: BTreeSet<Vec<usize>> = BTreeSet::new();
+
+        for record in &records {
+            let row_key = ItemOrder::key(&row_orders, record);
+            let col_key = ItemOrder::key(&col_orders, record);
+            col_keys.insert(col_key.clone());
+            let values = groups
+                .entry((row_key, col_key))
+                .or_insert_with(|| vec![Vec::new(); self.data_fields.len()]);
+            for (slot, data_field) in values.iter_mut().zip(&self.data_fields) {
+                if let Some(value) = record.get(data_field.field) {
+                    slot.push(value);
+                }
+            }
+        }
+        if col_keys.is_empty() {
+            col_keys.insert(Vec::new());
+        }
+
+        let mut header: Vec<CellValue> = self
+            .row_fields()
+            .map(|field| CellValue::String(field.display_name().to_string()))
+            .collect();
+        for col_key in col_keys.iter() {
+            let col_label: Vec<String> = col_key
+                .iter()
+                .zip(&col_orders)
+                .map(|(&pos, order)| order.values[pos].as_string())
+                .collect();
+            for data_field in &self.data_fields {
+                let mut label = col_label.clone();
+                if label.is_empty() || self.data_fields.len() > 1 {
+                    label.push(data_field.name.clone());
+                }
+                header.push(CellValue::String(label.join(" / ")));
+            }
+        }
+
+        let mut row_keys: Vec<&Vec<usize>> = groups.keys().map(|(row_key, _)| row_key).collect();
+        row_keys.dedup();
+
+        let mut grid = vec![header];
+        for row_key in row_keys {
+            let mut row: Vec<CellValue> = row_key
+                .iter()
+                .zip(&row_orders)
+                .map(|(&pos, order)| shared_item_to_cell_value(order.values[pos]))
+                .collect();
+            for col_key in col_keys.iter() {
+                let values = groups.get(&(row_key.clone(), col_key.clone()));
+                for (i, data_field) in self.data_fields.iter().enumerate() {
+                    row.push(match values {
+                        Some(values) => aggregate(data_field.subtotal, &values[i]),
+                        None => CellValue::Empty,
+                    });
+                }
+            }
+            grid.push(row);
+        }
+
+        grid
+    }
+}
+
+/// Values of each data field collected for one cell of the grid.
+type GroupValues<'a> = Vec<Vec<&'a SharedItem>>;
+
+/// Ordering of the distinct values of a field.
+struct ItemOrder<'a> {
+    field: usize,
+    values: Vec<&'a SharedItem>,
+}
+
+impl<'a> ItemOrder<'a> {
+    fn new(field: &'a PivotViewField, records: &[&'a [SharedItem]]) -> Self {
+        let mut values: Vec<&SharedItem> = field
+            .items
+            .iter()
+            .filter(|item| !item.hidden)
+            .map(|item| &item.value)
+            .collect();
+        for record in records {
+            if let Some(value) = record.get(field.index)
+                && !values.contains(&value)
+            {
+                values.push(value);
+            }
+        }
+        Self {
+            field: field.index,
+            values,
+        }
+    }
+
+    fn key(orders: &[ItemOrder<'_>], record: &[SharedItem]) -> Vec<usize> {
+        orders
+            .iter()
+            .map(|order| {
+                let value = record.get(order.field).unwrap_or(&SharedItem::Missing);
+                order
+                    .values
+                    .iter()
+                    .position(|v| *v == value)
+                    .unwrap_or(order.values.len())
+            })
+            .collect()
+    }
+}
+
+fn shared_item_to_cell_value(item: &SharedItem) -> CellValue {
+    match item {
+        SharedItem::Missing => CellValue::Empty,
+        SharedItem::Number(n) => CellValue::Float(*n),
+        SharedItem::Boolean(b) => CellValue::Bool(*b),
+        SharedItem::Error(e) => CellValue::Error(e.clone()),
+        SharedItem::String(s) | SharedItem::DateTime(s) => CellValue::String(s.clone()),
+    }
+}
+
+/// Aggregate values the way Excel does for a data field.
+fn aggregate(subtotal: Subtotal, values: &[&SharedItem]) -> CellValue {
+    let numbers: Vec<f64> = values
+        .iter()
+        .filter_map(|v| match v {
+            SharedItem::Number(n) => Some(*n),
+            _ => None,
+        })
+        .collect();
+    let n = numbers.len() as f64;
+    let mean = || numbers.iter().sum::<f64>() / n;
+    let variance = |sample: bool| {
+        let m = mean();
+        let sum_sq: f64 = numbers.iter().map(|x| (x - m).powi(2)).sum();
+        sum_sq / if sample { n - 1.0 } else { n }
+    };
+    let min_count = match subtotal {
+        Subtotal::StdDev | Subtotal::Var => 2,
+        Subtotal::Average
+        | Subtotal::Max
+        | Subtotal::Min
+        | Subtotal::Product
+        | Subtotal::StdDevP
+        | Subtotal::VarP => 1,
+        _ => 0,
+    };
+    if numbers.len() < min_count {
+        return CellValue::Empty;
+    }
+
+    let result = match subtotal {
+        Subtotal::Sum => numbers.iter().sum(),
+        Subtotal::Count => values
+            .iter()
+            .filter(|v| !matches!(v, SharedItem::Missing))
+            .count() as f64,
+        Subtotal::CountNums => n,
+        Subtotal::Average => mean(),
+        Subtotal::Max => numbers.iter().copied().fold(f64::MIN, f64::max),
+        Subtotal::Min => numbers.iter().copied().fold(f64::MAX, f64::min),
+        Subtotal::Product => numbers.iter().product(),
+        Subtotal::StdDev => variance(true).sqrt(),
+        Subtotal::StdDevP => variance(false).sqrt(),
+        Subtotal::Var => variance(true),
+        Subtotal::VarP => variance(false),
+    };
+    CellValue::Float(result)
+}
+
+/// Label Excel uses for a data field without a custom name.
+fn subtotal_label(subtotal: Subtotal) -> &'static str {
+    match subtotal {
+        Subtotal::Average => "Average",
+        Subtotal::Count => "Count",
+        Subtotal::CountNums => "Count",
+        Subtotal::Max => "Max",
+        Subtotal::Min => "Min",
+        Subtotal::Product => "Product",
+        Subtotal::StdDev => "StdDev",
+        Subtotal::StdDevP => "StdDevp",
+        Subtotal::Sum => "Sum",
+        Subtotal::Var => "Var",
+        Subtotal::VarP => "Varp",
+    }
+}
+
+/// The parts of a pivotTableDefinition needed to build a view, still as indexes.
+struct RawDefinition {
+    name: String,
+    location_ref: String,
+    pivot_fields: Vec<RawPivotField>,
+    row_fields: Vec<i64>,
+    column_fields: Vec<i64>,
+    page_fields: Vec<(usize, Option<usize>)>,
+    data_fields: Vec<RawDataField>,
+}
+
+#[derive(Default)]
+struct RawPivotField {
+    name: Option<String>,
+    axis: Option<AxisType>,
+    items: Vec<RawItem>,
+}
+
+struct RawItem {
+    x: Option<usize>,
+    item_type: Option<String>,
+    name: Option<String>,
+    hidden: bool,
+}
+
+struct RawDataField {
+    name: Option<String>,
+    field: usize,
+    subtotal: Subtotal,
+}
+
+impl RawDefinition {
+    fn parse(xml: &str) -> SheetResult<Option<Self>> {
+        let mut reader = Reader::from_str(xml);
+        let mut definition: Option<Self> = None;
+        let mut section: Option<Vec<u8>> = None;
+
+        loop {
+            let event = reader
+                .read_event()
+                .map_err(|e| format!("Failed to parse pivot table definition: {}", e))?;
+            match event {
+                Event::Start(ref e) | Event::Empty(ref e) => {
+                    let is_empty = matches!(event, Event::Empty(_));
+                    let local_name = e.local_name();
+                    let name = local_name.as_ref();
+                    if name == b"pivotTableDefinition" && definition.is_none() {
+                        definition = Some(Self {
+                            name: attr(e, b"name").unwrap_or_default(),
+                            location_ref: String::new(),
+                            pivot_fields: Vec::new(),
+                            row_fields: Vec::new(),
+                            column_fields: Vec::new(),
+                            page_fields: Vec::new(),
+                            data_fields: Vec::new(),
+                        });
+                        continue;
+                    }
+                    let Some(def) = definition.as_mut() else {
+                        continue;
+                    };
+
+                    match (section.as_deref(), name) {
+                        (_, b"location") => {
+                            def.location_ref = attr(e, b"ref").unwrap_or_default();
+                        },
+                        (
+                            _,
+                            b"pivotFields" | b"rowFields" | b"colFields" | b"pageFields"
+                            | b"dataFields",
+                        ) if !is_empty => section = Some(name.to_vec()),
+                        (Some(b"pivotFields"), b"pivotField") => {
+                            def.pivot_fields.push(RawPivotField {
+                                name: attr(e, b"name"),
+                                axis: attr(e, b"axis").map(|a| AxisType::parse_str(&a)),
+                                items: Vec::new(),
+                            });
+                        },
+                        (Some(b"pivotFields"), b"item") => {
+                            if let Some(field) = def.pivot_fields.last_mut() {
+                                field.items.push(RawItem {
+                                    x: attr(e, b"x").and_then(|x| x.parse().ok()),
+                                    item_type: attr(e, b"t"),
+                                    name: attr(e, b"n"),
+                                    hidden: attr(e, b"h").is_some_and(|h| h == "1" || h == "true"),
+                                });
+                            }
+                        },
+                        (Some(b"rowFields"), b"field") => {
+                            if let Some(x) = attr(e, b"x").and_then(|x| x.parse().ok()) {
+                                def.row_fields.push(x);
+                            }
+                        },
+                        (Some(b"colFields"), b"field") => {
+                            if let Some(x) = attr(e, b"x").and_then(|x| x.parse().ok()) {
+                                def.column_fields.push(x);
+                            }
+                        },
+                        (Some(b"pageFields"), b"pageField") => {
+                            if let Some(fld) = attr(e, b"fld").and_then(|f| f.parse().ok()) {
+                                let item = attr(e, b"item").and_then(|i| i.parse().ok());
+                                def.page_fields.push((fld, item));
+                            }
+                        },
+                        (Some(b"dataFields"), b"dataField") => {
+                            if let Some(fld) = attr(e, b"fld").and_then(|f| f.parse().ok()) {
+                                def.data_fields.push(RawDataField {
+                                    name: attr(e, b"name"),
+                                    field: fld,
+                                    subtotal: attr(e, b"subtotal")
+                                        .and_then(|s| Subtotal::parse_str(&s))
+                                        .unwrap_or(Subtotal::Sum),
+                                });
+                            }
+                        },
+                        _ => {},
+                    }
+                },
+                Event::End(ref e) if section.as_deref() == Some(e.local_name().as_ref()) => {
+                    section = None;
+                },
+                Event::Eof => break,
+                _ => {},
+            }
+        }
+
+        Ok(definition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::xlsx::pivot::{read_pivot_cache_definition, read_pivot_cache_records};
+
+    const CACHE_DEF: &str = r#"<pivotCacheDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" recordCount="5">
+<cacheSource type="worksheet"><worksheetSource ref="A1:C6" sheet="Data"/></cacheSource>
+<cacheFields count="3">
+<cacheField name="Region" numFmtId="0"><sharedItems count="2"><s v="West"/><s v="East"/></sharedItems></cacheField>
+<cacheField name="Product" numFmtId="0"><sharedItems count="3"><s v="Pens &amp; Ink"/><s v="Paper"/><s v="Clips"/></sharedItems></cacheField>
+<cacheField name="Amount" numFmtId="0"><sharedItems containsSemiMixedTypes="0" containsString="0" containsNumber="1" minValue="1" maxValue="40"/></cacheField>
+</cacheFields></pivotCacheDefinition>"#;
+
+    const RECORDS: &str = r#"<pivotCacheRecords xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="5">
+<r><x v="0"/><x v="0"/><n v="10"/></r>
+<r><x v="1"/><x v="1"/><n v="20"/></r>
+<r><x v="0"/><x v="1"/><n v="5"/></r>
+<r><x v="1"/><x v="0"/><n v="40"/></r>
+<r><x v="0"/><x v="2"/><n v="1"/></r>
+</pivotCacheRecords>"#;
+
+    const TABLE: &str = r#"<pivotTableDefinition xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" name="Sales" cacheId="1">
+<location ref="E3:H6" firstHeaderRow="1" firstDataRow="2" firstDataCol="1"/>
+<pivotFields count="3">
+<pivotField axis="axisRow" showAll="0"><items count="3"><item x="1"/><item x="0"/><item t="default"/></items></pivotField>
+<pivotField axis="axisCol" name="Item" showAll="0"><items count="4"><item x="1"/><item x="0"/><item x="2" h="1"/><item t="default"/></items></pivotField>
+<pivotField dataField="1" showAll="0"/>
+</pivotFields>
+<rowFields count="1"><field x="0"/></rowFields>
+<colFields count="2"><field x="1"/><field x="-2"/></colFields>
+<dataFields count="2"><dataField fld="2" baseField="0" baseItem="0"/><dataField name="Largest" fld="2" subtotal="max"/></dataFields>
+</pivotTableDefinition>"#;
+
+    fn view() -> PivotTableView {
+        let cache_def = read_pivot_cache_definition(CACHE_DEF).unwrap().unwrap();
+        let records = read_pivot_cache_records(RECORDS, &cache_def).unwrap();
+        PivotTableView::from_xml(TABLE, "Report", &cache_def, &records)
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn resolves_fields_and_items() {
+        let view = view();
+        assert_eq!(view.name, "Sales");
+        assert_eq!(view.location_ref, "E3:H6");
+        assert_eq!(view.records.len(), 5);
+
+        let rows: Vec<&str> = view.row_fields().map(|f| f.display_name()).collect();
+        assert_eq!(rows, ["Region"]);
+        let region = view.field_by_name("Region").unwrap();
+        let items: Vec<String> = region.items.iter().map(|i| i.value.as_string()).collect();
+        assert_eq!(items, ["East", "West"]);
+
+        let product = view.column_fields().next().unwrap();
+        assert_eq!(product.name, "Product");
+        assert_eq!(product.display_name(), "Item");
+        assert_eq!(product.items[0].value.as_string(), "Paper");
+        assert!(product.items[2].hidden);
+
+        assert_eq!(view.data_fields[0].name, "Sum of Amount");
+        assert_eq!(view.data_fields[0].subtotal, Subtotal::Sum);
+        assert_eq!(view.data_fields[1].name, "Largest");
+        assert_eq!(view.data_fields[1].subtotal, Subtotal::Max);
+    }
+
+    #[test]
+    fn flattens_to_grid() {
+        let grid = view().to_grid();
+        let s = |v: &str| CellValue::String(v.to_string());
+        assert_eq!(
+            grid,
+            vec![
+                vec![
+                    s("Region"),
+                    s("Paper / Sum of Amount"),
+                    s("Paper / Largest"),
+                    s("Pens & Ink / Sum of Amount"),
+                    s("Pens & Ink / Largest"),
+                ],
+                vec![
+                    s("East"),
+                    CellValue::Float(20.0),
+                    CellValue::Float(20.0),
+                    CellValue::Float(40.0),
+                    CellValue::Float(40.0),
+                ],
+                vec![
+                    s("West"),
+                    CellValue::Float(5.0),
+                    CellValue::Float(5.0),
+                    CellValue::Float(10.0),
+                    CellValue::Float(10.0),
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    fn page_field_selection_filters_records() {
+        let table = TABLE
+            .replace(
+                r#"<pivotField axis="axisRow""#,
+                r#"<pivotField axis="axisPage""#,
+            )
+            .replace(
+                r#"<rowFields count="1"><field x="0"/></rowFields>"#,
+                r#"<pageFields count="1"><pageField fld="0" item="1" hier="-1"/></pageFields>"#,
+            );
+        let cache_def = read_pivot_cache_definition(CACHE_DEF).unwrap().unwrap();
+        let records = read_pivot_cache_records(RECORDS, &cache_def).unwrap();
+        let view = PivotTableView::from_xml(&table, "Report", &cache_def, &records)
+            .unwrap()
+            .unwrap();
+
+        let (field, selected) = view.page_fields().next().unwrap();
+        assert_eq!(field.name, "Region");
+        assert_eq!(selected, Some(&SharedItem::String("West".to_string())));
+
+        let grid = view.to_grid();
+        assert_eq!(grid.len(), 2);
+        assert_eq!(
+            grid[1],
+            vec![
+                CellValue::Float(5.0),
+                CellValue::Float(5.0),
+                CellValue::Float(10.0),
+                CellValue::Float(10.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn pivot_table_views_round_trip() {
+        use crate::ooxml::pivot::{
+            PivotAxis, PivotDataField, PivotFieldRole, PivotTable, PivotValueFunction,
+        };
+        use crate::ooxml::xlsx::Workbook;
+
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        ws.set_name("Data".to_string());
+        let rows = [("West", 10), ("East", 20), ("West", 5)];
+        ws.set_cell_value(1, 1, "Region");
+        ws.set_cell_value(1, 2, "Amount");
+        for (i, (region, amount)) in rows.iter().enumerate() {
+            ws.set_cell_value(i as u32 + 2, 1, *region);
+            ws.set_cell_value(i as u32 + 2, 2, *amount as i64);
+        }
+        wb.add_worksheet("Report");
+        wb.add_pivot_table(PivotTable {
+            name: "Sales".to_string(),
+            source_sheet: Some("Data".to_string()),
+            source_ref: Some("A1:B4".to_string()),
+            field_names: vec!["Region".to_string(), "Amount".to_string()],
+            sheet_name: "Report".to_string(),
+            cache_id: 1,
+            location_ref: "A3".to_string(),
+            row_fields: vec![PivotFieldRole {
+                field_name: "Region".to_string(),
+                axis: PivotAxis::Row,
+                position: 0,
+            }],
+            column_fields: Vec::new(),
+            filter_fields: Vec::new(),
+            data_fields: vec![PivotDataField {
+                field_name: "Amount".to_string(),
+                function: PivotValueFunction::Sum,
+                display_name: None,
+            }],
+        })
+        .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pivot.xlsx");
+        wb.save(&path).unwrap();
+
+        let wb = Workbook::open(&path).unwrap();
+        let views = wb.pivot_table_views().unwrap();
+        assert_eq!(views.len(), 1);
+        let view = &views[0];
+        assert_eq!(view.sheet_name, "Report");
+        assert_eq!(view.row_fields().next().unwrap().name, "Region");
+        assert_eq!(view.data_fields[0].field, 1);
+
+        let s = |v: &str| CellValue::String(v.to_string());
+        assert_eq!(
+            view.to_grid(),
+            vec![
+                vec![s("Region"), s("Amount")],
+                vec![s("West"), CellValue::Float(15.0)],
+                vec![s("East"), CellValue::Float(20.0)],
+            ]
+        );
+    }
+}
@@ -0,0 +1,685 @@
+//! Cell references in formulas and range attributes.
+//!
+//! Structural edits move cells, so every reference to the edited sheet must
+//! move with them, and the cells of a shared formula group see the group's
+//! formula with its relative references moved to their own position.
+//! [`map_references`] finds the A1-style references in a formula and leaves
+//! everything else (strings, function names, defined names, structured
+//! references, references into other workbooks) untouched.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use super::Cell;
+
+/// Largest row number of a worksheet.
+pub(super) const MAX_ROW: u32 = 1_048_576;
+
+/// Largest column number of a worksheet.
+pub(super) const MAX_COL: u32 = 16_384;
+
+/// Which dimension a structural edit changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Axis {
+    Rows,
+    Columns,
+}
+
+/// Inserting or deleting a block of rows or columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Shift {
+    pub axis: Axis,
+    /// First row or column inserted or deleted (1-based)
+    pub at: u32,
+    pub count: u32,
+    pub delete: bool,
+}
+
+impl Shift {
+    fn max(&self) -> u32 {
+        match self.axis {
+            Axis::Rows => MAX_ROW,
+            Axis::Columns => MAX_COL,
+        }
+    }
+
+    /// New position of a row or column, `None` if it is deleted or pushed
+    /// off the sheet.
+    pub fn index(&self, index: u32) -> Option<u32> {
+        if index < self.at {
+            Some(index)
+        } else if self.delete {
+            (index >= self.at + self.count).then(|| index - self.count)
+        } else {
+            Some(index + self.count).filter(|&index| index <= self.max())
+        }
+    }
+
+    /// New bounds of a block of rows or columns, `None` if all of it is
+    /// deleted.
+    ///
+    /// Inserting inside the block grows it and deleting part of it shrinks
+    /// it, as Excel does.
+    pub fn span(&self, first: u32, last: u32) -> Option<(u32, u32)> {
+        if self.delete {
+            let end = self.at + self.count;
+            if first >= self.at && last < end {
+                return None;
+            }
+            let first = match first {
+                first if first < self.at => first,
+                first if first < end => self.at,
+                first => first - self.count,
+            };
+            let last = match last {
+                last if last < self.at => last,
+                last if last < end => self.at - 1,
+                last => last - self.count,
+            };
+            Some((first, last))
+        } else {
+            let first = self.index(first)?;
+            let last = if last >= self.at {
+                (last + self.count).min(self.max())
+            } else {
+                last
+            };
+            Some((first, last))
+        }
+    }
+
+    /// New position of a cell given as 1-based `(row, col)`.
+    pub fn cell(&self, row: u32, col: u32) -> Option<(u32, u32)> {
+        match self.axis {
+            Axis::Rows => Some((self.index(row)?, col)),
+            Axis::Columns => Some((row, self.index(col)?)),
+        }
+    }
+
+    /// The coordinate of a corner along the shifted axis.
+    fn coord<'a>(&self, corner: &'a mut Corner) -> Option<&'a mut Coord> {
+        match self.axis {
+            Axis::Rows => corner.row.as_mut(),
+            Axis::Columns => corner.col.as_mut(),
+        }
+    }
+
+    /// Move an area, `None` if the cells it refers to are deleted.
+    pub fn area(&self, mut area: Area) -> Option<Area> {
+        match area.last.as_mut() {
+            None => {
+                if let Some(coord) = self.coord(&mut area.first) {
+                    coord.index = self.index(coord.index)?;
+                }
+            },
+            Some(last) => {
+                if let (Some(a), Some(b)) = (self.coord(&mut area.first), self.coord(last)) {
+                    let (low, high) = self.span(a.index.min(b.index), a.index.max(b.index))?;
+                    if a.index <= b.index {
+                        (a.index, b.index) = (low, high);
+                    } else {
+                        (a.index, b.index) = (high, low);
+                    }
+                }
+            },
+        }
+        Some(area)
+    }
+}
+
+/// A row or column coordinate of a reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Coord {
+    /// 1-based row or column number
+    pub index: u32,
+    /// Whether the coordinate is written with `$`
+    pub absolute: bool,
+}
+
+/// One end of a reference.
+///
+/// Whole-column references have no row and whole-row references have no
+/// column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Corner {
+    pub col: Option<Coord>,
+    pub row: Option<Coord>,
+}
+
+/// A cell, a range, or whole rows or columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct Area {
+    pub first: Corner,
+    pub last: Option<Corner>,
+}
+
+impl Area {
+    /// Move the relative coordinates of the area, as when a formula is
+    /// copied to another cell. `None` if the result leaves the sheet.
+    pub fn offset(mut self, rows: i64, cols: i64) -> Option<Self> {
+        let offset = |coord: &mut Option<Coord>, delta: i64, max: u32| -> Option<()> {
+            if let Some(coord) = coord
+                && !coord.absolute
+            {
+                let index = i64::from(coord.index) + delta;
+                if index < 1 || index > i64::from(max) {
+                    return None;
+                }
+                coord.index = index as u32;
+            }
+            Some(())
+        };
+
+        for corner in std::iter::once(&mut self.first).chain(self.last.as_mut()) {
+            offset(&mut corner.row, rows, MAX_ROW)?;
+            offset(&mut corner.col, cols, MAX_COL)?;
+        }
+        Some(self)
+    }
+}
+
+impl fmt::Display for Corner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dollar = |coord: &Coord| if coord.absolute { "$" } else { "" };
+        if let Some(col) = &self.col {
+            write!(f, "{}{}", dollar(col), Cell::column_to_letters(col.index))?;
+        }
+        if let Some(row) = &self.row {
+            write!(f, "{}{}", dollar(row), row.index)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Area {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.first)?;
+        if let Some(last) = &self.last {
+            write!(f, ":{}", last)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parse one end of a reference starting at `start`.
+fn parse_corner(s: &[u8], start: usize) -> Option<(Corner, usize)> {
+    let mut i = start;
+    let mut dollar = s.get(i) == Some(&b'$');
+    if dollar {
+        i += 1;
+    }
+
+    let letters = i;
+    while s.get(i).is_some_and(u8::is_ascii_alphabetic) {
+        i += 1;
+    }
+    let col = if i > letters {
+        if i - letters > 3 {
+            return None;
+        }
+        let index = s[letters..i].iter().fold(0, |index, b| {
+            index * 26 + u32::from(b.to_ascii_uppercase() - b'A') + 1
+        });
+        if index > MAX_COL {
+            return None;
+        }
+        let col = Coord {
+            index,
+            absolute: dollar,
+        };
+        dollar = s.get(i) == Some(&b'$');
+        if dollar {
+            i += 1;
+        }
+        Some(col)
+    } else {
+        None
+    };
+
+    let digits = i;
+    while s.get(i).is_some_and(u8::is_ascii_digit) {
+        i += 1;
+    }
+    let row = if i > digits {
+        let index: u32 = std::str::from_utf8(&s[digits..i]).ok()?.parse().ok()?;
+        if index == 0 || index > MAX_ROW {
+            return None;
+        }
+        Some(Coord {
+            index,
+            absolute: dollar,
+        })
+    } else if dollar {
+        return None;
+    } else {
+        None
+    };
+
+    (col.is_some() || row.is_some()).then_some((Corner { col, row }, i))
+}
+
+/// Parse a reference starting at `start`, without checking what follows.
+fn parse_area(s: &[u8], start: usize) -> Option<(Area, usize)> {
+    let (first, mut end) = parse_corner(s, start)?;
+    let shape = |corner: &Corner| (corner.col.is_some(), corner.row.is_some());
+
+    let mut last = None;
+    if s.get(end) == Some(&b':')
+        && let Some((corner, corner_end)) = parse_corner(s, end + 1)
+        && shape(&corner) == shape(&first)
+    {
+        last = Some(corner);
+        end = corner_end;
+    }
+
+    // Whole rows and columns are only written as ranges
+    if last.is_none() && shape(&first) != (true, true) {
+        return None;
+    }
+    Some((Area { first, last }, end))
+}
+
+/// Parse a range attribute such as `A1:C3`.
+pub(super) fn parse_range(text: &str) -> Option<Area> {
+    let (area, end) = parse_area(text.as_bytes(), 0)?;
+    (end == text.len()).then_some(area)
+}
+
+/// Shift a range attribute, `None` if the range is deleted.
+///
+/// Values that are not ranges are returned unchanged.
+pub(super) fn shift_range(text: &str, shift: &Shift) -> Option<String> {
+    match parse_range(text) {
+        Some(area) => shift.area(area).map(|area| area.to_string()),
+        None => Some(text.to_string()),
+    }
+}
+
+/// Shift a space-separated list of ranges, `None` if all of them are
+/// deleted.
+pub(super) fn shift_sqref(text: &str, shift: &Shift) -> Option<String> {
+    let ranges: Vec<String> = text
+        .split_whitespace()
+        .filter_map(|range| shift_range(range, shift))
+        .collect();
+    (!ranges.is_empty()).then(|| ranges.join(" "))
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '.' | '\\' | '$' | '?')
+}
+
+/// End of the run of name characters starting at `start`.
+fn name_end(formula: &str, start: usize) -> usize {
+    formula[start..]
+        .char_indices()
+        .find(|&(_, c)| !is_name_char(c))
+        .map_or(formula.len(), |(offset, _)| start + offset)
+}
+
+/// Whether a reference may end at `end`.
+fn ends_reference(formula: &str, end: usize) -> bool {
+    formula[end..]
+        .chars()
+        .next()
+        .is_none_or(|c| !is_name_char(c) && !matches!(c, '(' | '!' | '['))
+}
+
+/// End of a literal delimited by `quote`, where a doubled quote escapes it.
+fn quoted_end(s: &[u8], start: usize, quote: u8) -> usize {
+    let mut i = start + 1;
+    while i < s.len() {
+        if s[i] == quote {
+            if s.get(i + 1) == Some(&quote) {
+                i += 2;
+                continue;
+            }
+            return i + 1;
+        }
+        i += 1;
+    }
+    s.len()
+}
+
+/// End of a bracketed group, allowing nested brackets.
+///
+/// Inside structured references `'` escapes the next character.
+fn bracket_end(s: &[u8], start: usize) -> usize {
+    let mut depth = 0;
+    let mut i = start;
+    while i < s.len() {
+        match s[i] {
+            b'\'' => i += 1,
+            b'[' => depth += 1,
+            b']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return i + 1;
+                }
+            },
+            _ => {},
+        }
+        i += 1;
+    }
+    s.len()
+}
+
+/// Rewrite every A1-style reference in a formula.
+///
+/// `map` receives the sheet the reference is qualified with (unquoted,
+/// `None` when unqualified) and the referenced area, and returns the new
+/// area or `None` to replace the reference with `#REF!`. References into
+/// other workbooks and across several sheets are left untouched.
+pub(super) fn map_references(
+    formula: &str,
+    mut map: impl FnMut(Option<&str>, Area) -> Option<Area>,
+) -> String {
+    let s = formula.as_bytes();
+    let mut out = String::with_capacity(formula.len());
+    let mut i = 0;
+    // Sheet qualifier for the reference that follows, if any
+    let mut qualifier: Option<String> = None;
+    // Whether the next reference points outside this workbook
+    let mut foreign = false;
+
+    let mut emit = |out: &mut String, start: usize, qualifier: Option<&str>, foreign: bool| {
+        let (area, end) = parse_area(s, start).filter(|&(_, end)| ends_reference(formula, end))?;
+        let original = &formula[start..end];
+        if foreign {
+            out.push_str(original);
+        } else {
+            match map(qualifier, area) {
+                Some(mapped) if mapped == area => out.push_str(original),
+                Some(mapped) => out.push_str(&mapped.to_string()),
+                None => out.push_str("#REF!"),
+            }
+        }
+        Some(end)
+    };
+
+    while i < s.len() {
+        // A qualifier applies only to the reference right after it
+        if let Some(sheet) = qualifier.take() {
+            let is_foreign = std::mem::take(&mut foreign);
+            if let Some(end) = emit(&mut out, i, Some(&sheet), is_foreign) {
+                i = end;
+                continue;
+            }
+        }
+
+        let c = formula[i..].chars().next().unwrap_or_default();
+        match c {
+            '"' => {
+                let end = quoted_end(s, i, b'"');
+                out.push_str(&formula[i..end]);
+                i = end;
+            },
+            '\'' => {
+                let end = quoted_end(s, i, b'\'');
+                out.push_str(&formula[i..end]);
+                if s.get(end) == Some(&b'!') {
+                    out.push('!');
+                    let sheet = formula[i + 1..end - 1].replace("''", "'");
+                    foreign |= sheet.contains(':') || sheet.contains('[');
+                    qualifier = Some(sheet);
+                    i = end + 1;
+                } else {
+                    i = end;
+                }
+            },
+            '[' => {
+                // `Table1[Column]` is a structured reference, `[1]Sheet1!A1`
+                // points into another workbook
+                let structured = formula[..i].chars().next_back().is_some_and(is_name_char);
+                let end = bracket_end(s, i);
+                out.push_str(&formula[i..end]);
+                foreign |= !structured;
+                i = end;
+            },
+            '#' => {
+                // Error literals such as `#REF!` and `#DIV/0!`
+                let mut end = i + 1;
+                while s
+                    .get(end)
+                    .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'/' | b'_'))
+                {
+                    end += 1;
+                }
+                if end > i + 1 && matches!(s.get(end), Some(b'!' | b'?')) {
+                    end += 1;
+                }
+                out.push_str(&formula[i..end]);
+                i = end;
+            },
+            c if is_name_char(c) => {
+                let end = name_end(formula, i);
+                match s.get(end) {
+                    Some(b'(') => {
+                        out.push_str(&formula[i..end]);
+                        i = end;
+                    },
+                    Some(b'!') => {
+                        out.push_str(&formula[i..=end]);
+                        qualifier = Some(formula[i..end].to_string());
+                        i = end + 1;
+                    },
+                    Some(b':')
+                        if name_end(formula, end + 1) > end + 1
+                            && s.get(name_end(formula, end + 1)) == Some(&b'!') =>
+                    {
+                        // `Sheet1:Sheet3!A1` spans several sheets
+                        let bang = name_end(formula, end + 1);
+                        out.push_str(&formula[i..=bang]);
+                        qualifier = Some(formula[i..bang].to_string());
+                        foreign = true;
+                        i = bang + 1;
+                    },
+                    _ => {
+                        let is_foreign = std::mem::take(&mut foreign);
+                        match emit(&mut out, i, None, is_foreign) {
+                            Some(reference_end) => i = reference_end,
+                            None => {
+                                out.push_str(&formula[i..end]);
+                                i = end;
+                            },
+                        }
+                    },
+                }
+            },
+            c => {
+                out.push(c);
+                i += c.len_utf8();
+            },
+        }
+    }
+
+    out
+}
+
+/// Whether a sheet qualifier names the given sheet.
+pub(super) fn same_sheet(a: &str, b: &str) -> bool {
+    a == b || a.to_lowercase() == b.to_lowercase()
+}
+
+/// Translate a formula as if it were copied `rows` down and `cols` right.
+pub(super) fn translate(formula: &str, rows: i64, cols: i64) -> String {
+    map_references(formula, |_, area| area.offset(rows, cols))
+}
+
+/// Master formulas of the shared formula groups of a worksheet.
+///
+/// Only the first cell of a group stores the formula text; the other cells
+/// name the group by its `si` index and hold the master formula translated
+/// to their own position.
+#[derive(Debug, Clone, Default)]
+pub(super) struct SharedFormulas {
+    /// Row, column and formula of each group's master cell
+    masters: HashMap<String, (u32, u32, String)>,
+}
+
+impl SharedFormulas {
+    /// Remember the formula of the master cell of group `si`.
+    pub fn insert(&mut self, si: &str, row: u32, col: u32, formula: &str) {
+        self.masters
+            .insert(si.to_string(), (row, col, formula.to_string()));
+    }
+
+    /// The formula of the cell at `row` and `col` in group `si`, `None` if
+    /// the group's master has not been seen.
+    pub fn get(&self, si: &str, row: u32, col: u32) -> Option<String> {
+        let (master_row, master_col, formula) = self.masters.get(si)?;
+        Some(translate(
+            formula,
+            i64::from(row) - i64::from(*master_row),
+            i64::from(col) - i64::from(*master_col),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift(axis: Axis, at: u32, count: u32, delete: bool) -> Shift {
+        Shift {
+            axis,
+            at,
+            count,
+            delete,
+        }
+    }
+
+    /// Apply a shift on `Sheet1` to a formula written on `Sheet1`.
+    fn apply(formula: &str, shift: &Shift) -> String {
+        map_references(formula, |sheet, area| {
+            if sheet.is_none_or(|sheet| same_sheet(sheet, "Sheet1")) {
+                shift.area(area)
+            } else {
+                Some(area)
+            }
+        })
+    }
+
+    #[test]
+    fn test_insert_rows() {
+        // Inserting two rows above row 3, as Excel adjusts each formula
+        let shift = shift(Axis::Rows, 3, 2, false);
+        let cases = [
+            ("A1", "A1"),
+            ("A3", "A5"),
+            ("$A$3", "$A$5"),
+            ("A$3+$B4", "A$5+$B6"),
+            ("SUM(A1:A3)", "SUM(A1:A5)"),
+            ("SUM(A2:B2)", "SUM(A2:B2)"),
+            ("SUM(A3:A4)", "SUM(A5:A6)"),
+            ("SUM(A:A)", "SUM(A:A)"),
+            ("SUM(3:3)", "SUM(5:5)"),
+            ("SUM(2:3)", "SUM(2:5)"),
+            ("Sheet2!A3", "Sheet2!A3"),
+            ("sheet1!A3", "sheet1!A5"),
+            ("'Sheet1'!A3:B4", "'Sheet1'!A5:B6"),
+            ("\"A3\"&A3", "\"A3\"&A5"),
+            ("LOG10(A3)", "LOG10(A5)"),
+            ("Table1[Col A3]", "Table1[Col A3]"),
+            ("[1]Sheet1!A3", "[1]Sheet1!A3"),
+            ("Sheet1:Sheet3!A3", "Sheet1:Sheet3!A3"),
+            ("IF(ISERROR(A3),#N/A,A3)", "IF(ISERROR(A5),#N/A,A5)"),
+            ("A1048575", "#REF!"),
+            ("A1:A1048576", "A1:A1048576"),
+            ("MyName+A3B", "MyName+A3B"),
+            ("1.5E+3*A3", "1.5E+3*A5"),
+        ];
+        for (formula, expected) in cases {
+            assert_eq!(apply(formula, &shift), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_delete_rows() {
+        // Deleting rows 3 and 4
+        let shift = shift(Axis::Rows, 3, 2, true);
+        let cases = [
+            ("A2", "A2"),
+            ("A3", "#REF!"),
+            ("A5", "A3"),
+            ("SUM(A1:A10)", "SUM(A1:A8)"),
+            ("SUM(A3:A4)", "SUM(#REF!)"),
+            ("SUM(A4:A6)", "SUM(A3:A4)"),
+            ("SUM(A1:A3)", "SUM(A1:A2)"),
+            ("Sheet1!A4", "Sheet1!#REF!"),
+            ("Sheet2!A4", "Sheet2!A4"),
+            ("SUM(3:4)", "SUM(#REF!)"),
+            ("SUM(1:5)", "SUM(1:3)"),
+            ("SUM(B:C)", "SUM(B:C)"),
+        ];
+        for (formula, expected) in cases {
+            assert_eq!(apply(formula, &shift), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_insert_and_delete_columns() {
+        let insert = shift(Axis::Columns, 2, 1, false);
+        let cases = [
+            ("A1", "A1"),
+            ("B1", "C1"),
+            ("$B$1", "$C$1"),
+            ("SUM(A1:B2)", "SUM(A1:C2)"),
+            ("SUM(B:B)", "SUM(C:C)"),
+            ("SUM(1:1)", "SUM(1:1)"),
+            ("XFD1", "#REF!"),
+        ];
+        for (formula, expected) in cases {
+            assert_eq!(apply(formula, &insert), expected, "{}", formula);
+        }
+
+        let delete = shift(Axis::Columns, 2, 1, true);
+        let cases = [
+            ("B1", "#REF!"),
+            ("C1", "B1"),
+            ("SUM(A1:C1)", "SUM(A1:B1)"),
+            ("SUM(B:B)", "SUM(#REF!)"),
+            ("XFD1", "XFC1"),
+            ("'My ''Sheet'''!C1", "'My ''Sheet'''!C1"),
+        ];
+        for (formula, expected) in cases {
+            assert_eq!(apply(formula, &delete), expected, "{}", formula);
+        }
+    }
+
+    #[test]
+    fn test_ranges() {
+        let shift = shift(Axis::Rows, 2, 3, true);
+        assert_eq!(shift_range("A1:C10", &shift).as_deref(), Some("A1:C7"));
+        assert_eq!(shift_range("A2:C4", &shift), None);
+        assert_eq!(shift_range("Table1", &shift).as_deref(), Some("Table1"));
+        assert_eq!(
+            shift_sqref("A1 A3 B5:B6", &shift).as_deref(),
+            Some("A1 B2:B3")
+        );
+        assert_eq!(shift_sqref("A2:A4", &shift), None);
+    }
+
+    #[test]
+    fn test_translate() {
+        assert_eq!(translate("A1+$B$1+B$2+$C3", 2, 1), "B3+$B$1+C$2+$C5");
+        assert_eq!(translate("A1", -1, 0), "#REF!");
+        assert_eq!(translate("SUM(A:A)", 5, 1), "SUM(B:B)");
+    }
+
+    #[test]
+    fn test_shared_formulas() {
+        let mut shared = SharedFormulas::default();
+        assert_eq!(shared.get("0", 3, 1), None);
+        shared.insert("0", 2, 2, "A2*$C$1+SUM(A$1:A2)");
+        assert_eq!(
+            shared.get("0", 2, 2).as_deref(),
+            Some("A2*$C$1+SUM(A$1:A2)")
+        );
+        assert_eq!(
+            shared.get("0", 4, 3).as_deref(),
+            Some("B4*$C$1+SUM(B$1:B4)")
+        );
+        assert_eq!(shared.get("1", 4, 3), None);
+    }
+}
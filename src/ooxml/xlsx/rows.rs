@@ -0,0 +1,730 @@
+//! Lazy row iteration over XLSX worksheet XML.
+//!
+//! [`RowsIter`] walks the `<sheetData>` element of a worksheet part one
+//! `<row>` at a time and decodes only the row being returned, so memory use
+//! stays flat no matter how many rows the sheet has. Shared strings are
+//! borrowed from the workbook's table rather than cloned per cell.
+//!
+//! Worksheet parts that are still in the package file are decompressed as
+//! rows are reached instead of being read up front.
+//!
+//! Random access (`cell`, `range`, merged regions, styles, ...) still
+//! requires the eager [`Worksheet`](super::Worksheet) returned by
+//! [`Workbook::worksheet`](super::Workbook::worksheet).
+
+use std::borrow::Cow;
+use std::io::{BufRead, BufReader, Read};
+
+use memchr::memmem;
+
+use crate::common::xml::unescape_xml;
+use crate::sheet::{DateSystem, Result, Row, RowValue};
+
+use super::references::SharedFormulas;
+use super::worksheet::Worksheet;
+use super::{SharedStrings, Styles};
+
+/// Streaming iterator over the rows of an XLSX worksheet.
+///
+/// Created by [`Worksheet::rows_iter`] or
+/// [`Workbook::rows_iter`](super::Workbook::rows_iter). Rows are yielded in
+/// document order with 1-based row and column indices, matching the eager
+/// [`Worksheet`] API. Rows without any non-empty cell are still yielded so
+/// that row numbers can be tracked, but their [`Row::cells`] is empty.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::ooxml::xlsx::Workbook;
+///
+/// let workbook = Workbook::open("huge.xlsx")?;
+/// // Skip the header row, then process each data row once.
+/// for row in workbook.rows_iter(0)?.skip_rows(1) {
+///     let row = row?;
+///     for (col, value) in row.cells() {
+///         println!("R{}C{}: {:?}", row.index(), col, value);
+///     }
+/// }
+/// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+#[derive(Debug)]
+pub struct RowsIter<'a> {
+    /// Where the rows are read from
+    source: Source<'a>,
+    /// Index of the previously returned row, for rows without an `r` attribute
+    last_row: u32,
+    shared_strings: &'a SharedStrings,
+    /// Cell formats and date system for reading date-formatted numbers
+    date_styles: Option<(&'a Styles, DateSystem)>,
+    /// Shared formula groups whose master cell has been read
+    shared_formulas: SharedFormulas,
+}
+
+/// Worksheet XML that rows are read from.
+#[derive(Debug)]
+enum Source<'a> {
+    /// Contents of the `<sheetData>` element, with the byte offset of the
+    /// next unread row
+    Slice { data: &'a str, pos: usize },
+    /// A worksheet part decompressed as its rows are read
+    Stream(RowReader<'a>),
+}
+
+impl<'a> RowsIter<'a> {
+    /// Create an iterator over the worksheet XML `content`.
+    pub(crate) fn new(content: &'a str, shared_strings: &'a SharedStrings) -> Self {
+        let data = match content.find("<sheetData") {
+            Some(start) => {
+                let body = &content[start..];
+                match body.find('>') {
+                    // `<sheetData/>` has no rows
+                    Some(gt) if body[..gt].ends_with('/') => "",
+                    Some(gt) => {
+                        let body = &body[gt + 1..];
+                        body.find("</sheetData>").map_or(body, |end| &body[..end])
+                    },
+                    None => "",
+                }
+            },
+            None => "",
+        };
+
+        Self::with_source(Source::Slice { data, pos: 0 }, shared_strings)
+    }
+
+    /// Create an iterator that reads the worksheet XML from `reader` one row
+    /// at a time.
+    ///
+    /// Only the row being decoded is buffered, so values are copied out of
+    /// it instead of borrowed.
+    pub(crate) fn from_reader(
+        reader: impl Read + Send + 'a,
+        shared_strings: &'a SharedStrings,
+    ) -> Self {
+        Self::with_source(Source::Stream(RowReader::new(reader)), shared_strings)
+    }
+
+    fn with_source(source: Source<'a>, shared_strings: &'a SharedStrings) -> Self {
+        Self {
+            source,
+            last_row: 0,
+            shared_strings,
+            date_styles: None,
+            shared_formulas: SharedFormulas::default(),
+        }
+    }
+
+    /// Read numbers in cells formatted as dates as [`RowValue::DateTime`],
+    /// using the cell formats of `styles` and the workbook's `date_system`.
+    pub(crate) fn with_date_styles(mut self, styles: &'a Styles, date_system: DateSystem) -> Self {
+        self.date_styles = Some((styles, date_system));
+        self
+    }
+
+    /// Skip the next `n` rows without decoding their cells.
+    ///
+    /// This only scans for row boundaries, so skipping leading rows is much
+    /// cheaper than iterating over them. Rows holding shared formulas are
+    /// still read so that later cells using them can be expanded.
+    pub fn skip_rows(mut self, n: usize) -> Self {
+        for _ in 0..n {
+            if self.skip_row().is_none() {
+                break;
+            }
+        }
+        self
+    }
+
+    /// Advance past the next row without decoding it, except for the master
+    /// formulas of shared formula groups that later rows may use.
+    fn skip_row(&mut self) -> Option<()> {
+        if let Source::Stream(_) = self.source {
+            return self
+                .next_streamed_row(|rows, index, body| {
+                    if memmem::find(body.as_bytes(), b"\"shared\"").is_some() {
+                        let _ = rows.parse_row(index, body);
+                    }
+                    Ok(())
+                })
+                .map(drop);
+        }
+
+        let (tag, body) = self.next_row_span()?;
+        self.last_row = self.row_index(tag);
+        if memmem::find(body.as_bytes(), b"\"shared\"").is_some() {
+            let _ = self.parse_row(self.last_row, body);
+        }
+        Some(())
+    }
+
+    /// Advance past the next `<row>` element, returning its start tag and body.
+    fn next_row_span(&mut self) -> Option<(&'a str, &'a str)> {
+        let Source::Slice { data, pos } = &mut self.source else {
+            return None;
+        };
+        let data = *data;
+        let start = *pos + find_element(&data[*pos..], "<row")?;
+        let gt = start + data[start..].find('>')?;
+        let tag = &data[start..gt];
+
+        if tag.ends_with('/') {
+            *pos = gt + 1;
+            return Some((tag, ""));
+        }
+
+        let body_start = gt + 1;
+        let end = body_start + memmem::find(&data.as_bytes()[body_start..], b"</row>")?;
+        *pos = end + "</row>".len();
+        Some((tag, &data[body_start..end]))
+    }
+
+    /// Read the next row from the stream and pass its index and body to `f`.
+    ///
+    /// The row is only borrowed for the call, and its buffer is reused for
+    /// the next one. Returns `None` at the end of the rows or when reading
+    /// fails, see [`RowReader::error`].
+    fn next_streamed_row<T>(
+        &mut self,
+        f: impl FnOnce(&mut Self, u32, &str) -> Result<T>,
+    ) -> Option<Result<T>> {
+        let Source::Stream(reader) = &mut self.source else {
+            return None;
+        };
+        if !reader.next_row() {
+            return None;
+        }
+
+        let row = std::mem::take(&mut reader.row);
+        let result = match std::str::from_utf8(&row) {
+            Ok(row) => {
+                let (tag, body) = split_row(row);
+                let index = self.row_index(tag);
+                self.last_row = index;
+                f(self, index, body)
+            },
+            Err(err) => Err(err.into()),
+        };
+        if let Source::Stream(reader) = &mut self.source {
+            reader.row = row;
+        }
+        Some(result)
+    }
+
+    fn row_index(&self, tag: &str) -> u32 {
+        attribute(tag, "r")
+            .and_then(|r| r.parse().ok())
+            .unwrap_or(self.last_row + 1)
+    }
+
+    fn parse_row<'b>(&mut self, index: u32, body: &'b str) -> Result<Row<'b>>
+    where
+        'a: 'b,
+    {
+        let mut cells = Vec::new();
+        let mut last_col = 0;
+        let mut pos = 0;
+
+        while let Some(rel) = find_element(&body[pos..], "<c") {
+            let start = pos + rel;
+            let Some(gt_rel) = body[start..].find('>') else {
+                break;
+            };
+            let gt = start + gt_rel;
+            let tag = &body[start..gt];
+
+            let col = match attribute(tag, "r") {
+                Some(reference) => column_index(reference)
+                    .ok_or_else(|| format!("Invalid cell reference: {reference}"))?,
+                None => last_col + 1,
+            };
+            last_col = col;
+
+            // `<c r="A1" s="1"/>` carries formatting only
+            if tag.ends_with('/') {
+                pos = gt + 1;
+                continue;
+            }
+
+            let content_start = gt + 1;
+            let Some(end_rel) = memmem::find(&body.as_bytes()[content_start..], b"</c>") else {
+                break;
+            };
+            let content_end = content_start + end_rel;
+            pos = content_end + "</c>".len();
+
+            let mut value = self.parse_cell(index, col, tag, &body[content_start..content_end]);
+            if let Some((styles, date_system)) = self.date_styles
+                && attribute(tag, "s")
+                    .and_then(|s| s.parse().ok())
+                    .is_some_and(|style| styles.is_date_style(style))
+            {
+                value = value.into_date(date_system);
+            }
+            if value != RowValue::Empty {
+                cells.push((col, value));
+            }
+        }
+
+        Ok(Row::new(index, cells))
+    }
+
+    fn parse_cell<'b>(&mut self, row: u32, col: u32, tag: &'b str, content: &'b str) -> RowValue<'b>
+    where
+        'a: 'b,
+    {
+        let cell_type = attribute(tag, "t");
+
+        if cell_type == Some("inlineStr") || content.contains("<is>") {
+            let text = Worksheet::extract_inline_string_text(content).unwrap_or_default();
+            return RowValue::String(unescape(Cow::Owned(text)));
+        }
+
+        let value = element_text(content, "<v>", "</v>");
+        let base_value = match (cell_type, value) {
+            (Some("s"), Some(v)) => atoi_simd::parse::<usize, false, false>(v.as_bytes())
+                .ok()
+                .and_then(|index| self.shared_strings.get(index))
+                .map_or_else(
+                    || RowValue::Error(Cow::Borrowed("Invalid shared string reference")),
+                    |s| RowValue::String(Cow::Borrowed(s)),
+                ),
+            (Some("str"), Some(v)) => RowValue::String(unescape(Cow::Borrowed(v))),
+            (Some("e"), Some(v)) => RowValue::Error(Cow::Borrowed(v)),
+            (Some("b"), Some(v)) => match v {
+                "1" => RowValue::Bool(true),
+                "0" => RowValue::Bool(false),
+                _ => RowValue::Error(Cow::Borrowed("Invalid boolean value")),
+            },
+            (_, Some(v)) => {
+                if let Ok(int_val) = atoi_simd::parse::<_, false, false>(v.as_bytes()) {
+                    RowValue::Int(int_val)
+                } else if let Ok(float_val) = fast_float2::parse(v) {
+                    RowValue::Float(float_val)
+                } else {
+                    RowValue::String(unescape(Cow::Borrowed(v)))
+                }
+            },
+            _ => RowValue::Empty,
+        };
+
+        let Some((f_tag, text)) = formula(content) else {
+            return base_value;
+        };
+        let formula_type = attribute(f_tag, "t");
+        let formula = match (formula_type, attribute(f_tag, "si")) {
+            (Some("shared"), Some(si)) => match text.filter(|text| !text.is_empty()) {
+                Some(text) => {
+                    let text = unescape(Cow::Borrowed(text));
+                    self.shared_formulas.insert(si, row, col, &text);
+                    Some(text)
+                },
+                None => self.shared_formulas.get(si, row, col).map(Cow::Owned),
+            },
+            _ => text.map(|text| unescape(Cow::Borrowed(text))),
+        };
+
+        match formula {
+            Some(formula) => {
+                let is_array = formula_type == Some("array");
+                RowValue::Formula {
+                    formula,
+                    cached_value: match base_value {
+                        RowValue::Empty => None,
+                        other => Some(Box::new(other)),
+                    },
+                    is_array,
+                    array_range: attribute(f_tag, "ref")
+                        .filter(|_| is_array)
+                        .map(Cow::Borrowed),
+                }
+            },
+            None => base_value,
+        }
+    }
+}
+
+impl<'a> Iterator for RowsIter<'a> {
+    type Item = Result<Row<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Source::Stream(_) = self.source {
+            // Values borrowing the row buffer are copied before it is reused
+            return self
+                .next_streamed_row(|rows, index, body| {
+                    let row = rows.parse_row(index, body)?;
+                    let cells = row.cells().iter().map(|(col, value)| (*col, detach(value)));
+                    Ok(Row::new(row.index(), cells.collect()))
+                })
+                .or_else(|| match &mut self.source {
+                    Source::Stream(reader) => reader.error.take().map(|err| Err(err.into())),
+                    Source::Slice { .. } => None,
+                });
+        }
+
+        let (tag, body) = self.next_row_span()?;
+        let index = self.row_index(tag);
+        self.last_row = index;
+        Some(self.parse_row(index, body))
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        for _ in 0..n {
+            self.skip_row()?;
+        }
+        self.next()
+    }
+}
+
+/// Reads the `<row>` elements of a worksheet XML stream one at a time.
+struct RowReader<'a> {
+    reader: BufReader<Box<dyn Read + Send + 'a>>,
+    /// The last row read, from `<row` up to and including its end tag
+    row: Vec<u8>,
+    /// Whether the `<sheetData>` start tag has been read
+    in_sheet_data: bool,
+    /// Whether the end of the rows has been reached
+    done: bool,
+    /// The error that ended reading, until it is reported
+    error: Option<std::io::Error>,
+}
+
+impl<'a> RowReader<'a> {
+    fn new(reader: impl Read + Send + 'a) -> Self {
+        Self {
+            reader: BufReader::new(Box::new(reader)),
+            row: Vec::new(),
+            in_sheet_data: false,
+            done: false,
+            error: None,
+        }
+    }
+
+    /// Read the next row into `self.row`, returning false at the end of the
+    /// rows or when reading fails.
+    fn next_row(&mut self) -> bool {
+        if self.done {
+            return false;
+        }
+        match self.read_row() {
+            Ok(true) => true,
+            Ok(false) => {
+                self.done = true;
+                false
+            },
+            Err(err) => {
+                self.done = true;
+                self.error = Some(err);
+                false
+            },
+        }
+    }
+
+    fn read_row(&mut self) -> std::io::Result<bool> {
+        self.row.clear();
+        let mut in_row = false;
+        loop {
+            // Every tag ends a chunk, so start and end tags always close one
+            let start = self.row.len();
+            if self.reader.read_until(b'>', &mut self.row)? == 0 {
+                return Ok(false);
+            }
+            let chunk = &self.row[start..];
+
+            if in_row {
+                if chunk.ends_with(b"</row>") {
+                    return Ok(true);
+                }
+            } else if !self.in_sheet_data {
+                if find_element_bytes(chunk, "<sheetData").is_some() {
+                    // `<sheetData/>` has no rows
+                    if chunk.ends_with(b"/>") {
+                        return Ok(false);
+                    }
+                    self.in_sheet_data = true;
+                }
+                self.row.clear();
+            } else if let Some(tag) = find_element_bytes(chunk, "<row") {
+                self.row.drain(..start + tag);
+                if self.row.ends_with(b"/>") {
+                    return Ok(true);
+                }
+                in_row = true;
+            } else if memmem::find(chunk, b"</sheetData>").is_some() {
+                return Ok(false);
+            } else {
+                self.row.clear();
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for RowReader<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RowReader")
+            .field("in_sheet_data", &self.in_sheet_data)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Split a `<row>` element into its start tag and body.
+fn split_row(row: &str) -> (&str, &str) {
+    let gt = row.find('>').unwrap_or(row.len());
+    let tag = &row[..gt];
+    if tag.ends_with('/') {
+        return (tag, "");
+    }
+    let body = &row[(gt + 1).min(row.len())..];
+    (tag, body.strip_suffix("</row>").unwrap_or(body))
+}
+
+/// Copy the text a value borrows from a streamed row.
+fn detach<'b>(value: &RowValue<'_>) -> RowValue<'b> {
+    let owned = |text: &Cow<'_, str>| Cow::Owned(text.to_string());
+    match value {
+        RowValue::Empty => RowValue::Empty,
+        RowValue::Bool(b) => RowValue::Bool(*b),
+        RowValue::Int(i) => RowValue::Int(*i),
+        RowValue::Float(f) => RowValue::Float(*f),
+        RowValue::String(s) => RowValue::String(owned(s)),
+        RowValue::DateTime(d) => RowValue::DateTime(*d),
+        RowValue::Error(e) => RowValue::Error(owned(e)),
+        RowValue::Formula {
+            formula,
+            cached_value,
+            is_array,
+            array_range,
+        } => RowValue::Formula {
+            formula: owned(formula),
+            cached_value: cached_value.as_deref().map(|value| Box::new(detach(value))),
+            is_array: *is_array,
+            array_range: array_range.as_ref().map(owned),
+        },
+    }
+}
+
+/// Find the next `name` element start, ignoring longer tag names that share
+/// the prefix (e.g. `<cols` when looking for `<c`).
+fn find_element(haystack: &str, name: &str) -> Option<usize> {
+    find_element_bytes(haystack.as_bytes(), name)
+}
+
+/// [`find_element`] over bytes that may not be valid UTF-8 yet.
+fn find_element_bytes(bytes: &[u8], name: &str) -> Option<usize> {
+    let finder = memmem::Finder::new(name);
+    let mut pos = 0;
+    while let Some(rel) = finder.find(&bytes[pos..]) {
+        let start = pos + rel;
+        match bytes.get(start + name.len()) {
+            Some(b' ' | b'>' | b'/' | b'\t' | b'\r' | b'\n') => return Some(start),
+            Some(_) => pos = start + name.len(),
+            None => return None,
+        }
+    }
+    None
+}
+
+/// Extract an attribute value from a start tag without allocating.
+fn attribute<'t>(tag: &'t str, name: &str) -> Option<&'t str> {
+    let bytes = tag.as_bytes();
+    let mut pos = 0;
+    while let Some(rel) = memmem::find(&bytes[pos..], name.as_bytes()) {
+        let start = pos + rel;
+        let after = start + name.len();
+        let preceded_by_space = start > 0 && bytes[start - 1].is_ascii_whitespace();
+        if preceded_by_space && tag[after..].starts_with("=\"") {
+            let value_start = after + 2;
+            return tag[value_start..]
+                .find('"')
+                .map(|end| &tag[value_start..value_start + end]);
+        }
+        pos = after;
+    }
+    None
+}
+
+/// Text between the first `open` and the following `close` marker.
+fn element_text<'t>(content: &'t str, open: &str, close: &str) -> Option<&'t str> {
+    let start = content.find(open)? + open.len();
+    let end = content[start..].find(close)?;
+    Some(&content[start..start + end])
+}
+
+/// Extract the `<f>` start tag and formula text. Cells of a shared formula
+/// group other than its master (`<f t="shared" si="0"/>`) carry no text.
+fn formula(content: &str) -> Option<(&str, Option<&str>)> {
+    let start = find_element(content, "<f")?;
+    let gt = start + content[start..].find('>')?;
+    let tag = &content[start..gt];
+    if tag.ends_with('/') {
+        return Some((tag, None));
+    }
+    Some((tag, element_text(&content[gt..], ">", "</f>")))
+}
+
+/// Convert the column letters of an `A1` style reference into a 1-based index.
+fn column_index(reference: &str) -> Option<u32> {
+    let mut col = 0u32;
+    for b in reference.bytes() {
+        if b.is_ascii_alphabetic() {
+            col = col
+                .checked_mul(26)?
+                .checked_add((b.to_ascii_uppercase() - b'A' + 1) as u32)?;
+        } else if b.is_ascii_digit() {
+            break;
+        } else {
+            return None;
+        }
+    }
+    (col > 0).then_some(col)
+}
+
+fn unescape(text: Cow<'_, str>) -> Cow<'_, str> {
+    if text.contains('&') {
+        Cow::Owned(unescape_xml(&text))
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shared_strings() -> SharedStrings {
+        SharedStrings::parse(
+            r#"<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main"><si><t>alpha</t></si><si><t>beta</t></si></sst>"#,
+        )
+        .unwrap()
+    }
+
+    const SHEET: &str = r#"<worksheet><cols><col min="1" max="2" width="10"/></cols><sheetData>
+<row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c></row>
+<row r="2" ht="20" customHeight="1"><c r="A2"><v>42</v></c><c r="B2" s="1"/><c r="C2"><v>1.5</v></c></row>
+<row r="4"/>
+<row r="5"><c r="A5" t="b"><v>1</v></c><c r="AA5" t="inlineStr"><is><t>x &amp; y</t></is></c></row>
+<row r="6"><c r="A6"><f>SUM(A2:C2)</f><v>43.5</v></c><c r="B6" t="e"><v>#DIV/0!</v></c><c r="C6" t="str"><f>"a"&amp;"b"</f><v>ab</v></c></row>
+</sheetData><mergeCells count="1"><mergeCell ref="A1:B1"/></mergeCells></worksheet>"#;
+
+    #[test]
+    fn rows_iter_decodes_rows_lazily() {
+        let sst = shared_strings();
+        let rows: Vec<Row<'_>> = RowsIter::new(SHEET, &sst).collect::<Result<_>>().unwrap();
+
+        assert_eq!(
+            rows.iter().map(Row::index).collect::<Vec<_>>(),
+            [1, 2, 4, 5, 6]
+        );
+
+        // Shared strings are borrowed, not cloned
+        match rows[0].get(1) {
+            Some(RowValue::String(Cow::Borrowed(s))) => assert_eq!(*s, "alpha"),
+            other => panic!("expected borrowed shared string, got {other:?}"),
+        }
+        assert_eq!(rows[0].get(2).and_then(RowValue::as_str), Some("beta"));
+
+        assert_eq!(
+            rows[1].cells(),
+            &[(1, RowValue::Int(42)), (3, RowValue::Float(1.5))]
+        );
+        assert!(rows[2].is_empty());
+        assert_eq!(rows[3].get(1), Some(&RowValue::Bool(true)));
+        assert_eq!(rows[3].get(27).and_then(RowValue::as_str), Some("x & y"));
+
+        assert_eq!(
+            rows[4].get(1),
+            Some(&RowValue::Formula {
+                formula: Cow::Borrowed("SUM(A2:C2)"),
+                cached_value: Some(Box::new(RowValue::Float(43.5))),
+                is_array: false,
+                array_range: None,
+            })
+        );
+        assert_eq!(
+            rows[4].get(2),
+            Some(&RowValue::Error(Cow::Borrowed("#DIV/0!")))
+        );
+        match rows[4].get(3) {
+            Some(RowValue::Formula { formula, .. }) => assert_eq!(formula, "\"a\"&\"b\""),
+            other => panic!("expected formula, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn rows_iter_skips_leading_rows() {
+        let sst = shared_strings();
+        let mut rows = RowsIter::new(SHEET, &sst).skip_rows(2);
+        assert_eq!(rows.next().unwrap().unwrap().index(), 4);
+        assert_eq!(rows.nth(1).unwrap().unwrap().index(), 6);
+        assert!(rows.next().is_none());
+
+        assert_eq!(RowsIter::new(SHEET, &sst).skip_rows(10).count(), 0);
+    }
+
+    #[test]
+    fn rows_iter_handles_missing_references_and_empty_sheets() {
+        let sst = shared_strings();
+        let xml = "<sheetData><row><c><v>1</v></c><c><v>2</v></c></row><row><c t=\"s\"><v>9</v></c></row></sheetData>";
+        let rows: Vec<_> = RowsIter::new(xml, &sst).collect::<Result<_>>().unwrap();
+        assert_eq!(rows[0].index(), 1);
+        assert_eq!(
+            rows[0].cells(),
+            &[(1, RowValue::Int(1)), (2, RowValue::Int(2))]
+        );
+        assert_eq!(rows[1].index(), 2);
+        assert!(matches!(rows[1].get(1), Some(RowValue::Error(_))));
+
+        assert_eq!(
+            RowsIter::new("<worksheet><sheetData/></worksheet>", &sst).count(),
+            0
+        );
+        assert_eq!(RowsIter::new("<worksheet/>", &sst).count(), 0);
+    }
+
+    #[test]
+    fn rows_iter_reads_rows_from_a_stream() {
+        let sst = shared_strings();
+        let expected: Vec<_> = RowsIter::new(SHEET, &sst).collect::<Result<_>>().unwrap();
+        let stream = std::io::BufReader::with_capacity(7, SHEET.as_bytes());
+        let rows: Vec<_> = RowsIter::from_reader(stream, &sst)
+            .collect::<Result<_>>()
+            .unwrap();
+        assert_eq!(rows, expected);
+
+        let mut rows = RowsIter::from_reader(SHEET.as_bytes(), &sst).skip_rows(2);
+        assert_eq!(rows.next().unwrap().unwrap().index(), 4);
+        assert_eq!(rows.nth(1).unwrap().unwrap().index(), 6);
+        assert!(rows.next().is_none());
+
+        for xml in ["<worksheet><sheetData/></worksheet>", "<worksheet/>"] {
+            assert_eq!(RowsIter::from_reader(xml.as_bytes(), &sst).count(), 0);
+        }
+    }
+
+    #[test]
+    fn rows_iter_reports_stream_errors() {
+        let sst = shared_strings();
+        let truncated = &SHEET.as_bytes()[..SHEET.find("<row r=\"4\"").unwrap()];
+        let failing = truncated.chain(FailingReader);
+        let mut rows = RowsIter::from_reader(failing, &sst);
+        assert_eq!(rows.next().unwrap().unwrap().index(), 1);
+        assert_eq!(rows.next().unwrap().unwrap().index(), 2);
+        assert!(rows.next().unwrap().is_err());
+        assert!(rows.next().is_none());
+    }
+
+    struct FailingReader;
+
+    impl Read for FailingReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("corrupt stream"))
+        }
+    }
+
+    #[test]
+    fn rows_iter_rejects_invalid_references() {
+        let sst = shared_strings();
+        let xml = r#"<sheetData><row r="1"><c r="1A"><v>1</v></c></row></sheetData>"#;
+        assert!(RowsIter::new(xml, &sst).next().unwrap().is_err());
+    }
+}
@@ -9,8 +9,10 @@
 //! - Uses FxHashMap for better performance (if available)
 
 use std::collections::HashMap;
+use std::io::BufRead;
 
 use super::RichTextRun;
+use crate::common::xml::unescape_xml;
 use crate::sheet::Result;
 
 // Performance: Pre-allocate typical capacities to reduce reallocations
@@ -36,42 +38,77 @@ impl SharedStrings {
 
     /// Parse shared strings from xl/sharedStrings.xml content - optimized version.
     pub fn parse(content: &str) -> Result<Self> {
-        let mut strings = Vec::with_capacity(INITIAL_STRINGS_CAPACITY);
-        let string_to_index = HashMap::with_capacity(INITIAL_STRINGS_CAPACITY);
-        let mut rich_text: HashMap<usize, Vec<RichTextRun>> =
-            HashMap::with_capacity(INITIAL_STRINGS_CAPACITY / 4);
-
+        let mut table = Self::with_default_capacity();
         let bytes = content.as_bytes();
         let mut pos = 0;
 
         while let Some(si_start) = memchr::memmem::find(&bytes[pos..], b"<si>") {
             let si_start_pos = pos + si_start;
             if let Some(si_end) = memchr::memmem::find(&bytes[si_start_pos..], b"</si>") {
-                let si_content = &content[si_start_pos..si_start_pos + si_end + 5];
-
-                if let Some(text) = Self::extract_text_from_si(si_content) {
-                    let index = strings.len();
-                    // Performance: Always push to maintain order, deduplication handled by Excel
-                    strings.push(text);
-
-                    if let Some(runs) = Self::extract_rich_text_runs_from_si(si_content)
-                        && !runs.is_empty()
-                    {
-                        rich_text.insert(index, runs);
-                    }
-                }
-
+                table.push_si(&content[si_start_pos..si_start_pos + si_end + 5]);
                 pos = si_start_pos + si_end + 5;
             } else {
                 break;
             }
         }
 
-        Ok(SharedStrings {
-            strings,
-            string_to_index,
-            rich_text,
-        })
+        Ok(table)
+    }
+
+    /// Parse shared strings from a reader over xl/sharedStrings.xml.
+    ///
+    /// Only one `<si>` element is buffered at a time, so the table can be built
+    /// straight from a decompressing stream without materializing the whole
+    /// part, which matters for multi-gigabyte shared strings tables.
+    pub fn parse_reader<R: BufRead>(mut reader: R) -> Result<Self> {
+        let mut table = Self::with_default_capacity();
+        let mut buf = Vec::new();
+        let mut in_si = false;
+
+        loop {
+            if !in_si {
+                buf.clear();
+            }
+            // Every tag ends a chunk, so "<si>" and "</si>" always close one
+            if reader.read_until(b'>', &mut buf)? == 0 {
+                break;
+            }
+
+            if !in_si {
+                if buf.ends_with(b"<si>") {
+                    buf.drain(..buf.len() - 4);
+                    in_si = true;
+                }
+            } else if buf.ends_with(b"</si>") {
+                table.push_si(std::str::from_utf8(&buf)?);
+                in_si = false;
+            }
+        }
+
+        Ok(table)
+    }
+
+    fn with_default_capacity() -> Self {
+        SharedStrings {
+            strings: Vec::with_capacity(INITIAL_STRINGS_CAPACITY),
+            string_to_index: HashMap::with_capacity(INITIAL_STRINGS_CAPACITY),
+            rich_text: HashMap::with_capacity(INITIAL_STRINGS_CAPACITY / 4),
+        }
+    }
+
+    /// Add the string of one `<si>...</si>` element.
+    fn push_si(&mut self, si_content: &str) {
+        let index = self.strings.len();
+        // Performance: Always push to maintain order, deduplication handled by Excel.
+        // Empty items are kept too, since cells refer to strings by position.
+        self.strings
+            .push(Self::extract_text_from_si(si_content).unwrap_or_default());
+
+        if let Some(runs) = Self::extract_rich_text_runs_from_si(si_content)
+            && !runs.is_empty()
+        {
+            self.rich_text.insert(index, runs);
+        }
     }
 
     /// Get a string by its index.
@@ -100,13 +137,24 @@ impl SharedStrings {
     }
 
     /// Extract text content from <si> element - optimized version.
+    ///
+    /// An <si> may contain multiple <r><t>...</t></r> runs for rich text; the
+    /// text from all <t> elements is concatenated in document order.
     fn extract_text_from_si(si_content: &str) -> Option<String> {
-        let bytes = si_content.as_bytes();
+        let result = Self::concat_t_elements(si_content);
+        if result.is_empty() {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Concatenate and unescape the text of every <t> element in `content`.
+    fn concat_t_elements(content: &str) -> String {
+        let bytes = content.as_bytes();
         let mut result = String::new();
         let mut search_start = 0;
 
-        // An <si> may contain multiple <r><t>...</t></r> runs for rich text. Concatenate
-        // the text from all <t> elements in document order.
         while let Some(rel_pos) = memchr::memmem::find(&bytes[search_start..], b"<t") {
             let t_pos = search_start + rel_pos;
 
@@ -118,11 +166,22 @@ impl SharedStrings {
             };
             let text_start = t_pos + gt_rel + 1;
 
+            // <t/> has no text
+            if bytes[text_start - 2] == b'/' {
+                search_start = text_start;
+                continue;
+            }
+
             // Find the corresponding </t> end tag.
             let after_text = &bytes[text_start..];
             if let Some(end_rel) = memchr::memmem::find(after_text, b"</t>") {
                 let text_end = text_start + end_rel;
-                result.push_str(&si_content[text_start..text_end]);
+                let text = &content[text_start..text_end];
+                if text.contains('&') {
+                    result.push_str(&unescape_xml(text));
+                } else {
+                    result.push_str(text);
+                }
                 // Advance search past this </t>
                 search_start = text_end + 4; // len("</t>")
             } else {
@@ -130,11 +189,7 @@ impl SharedStrings {
             }
         }
 
-        if result.is_empty() {
-            None
-        } else {
-            Some(result)
-        }
+        result
     }
 
     /// Extract rich text runs (if any) from an <si> element.
@@ -196,27 +251,7 @@ impl SharedStrings {
 
     fn parse_rich_text_run(content: &str) -> Option<RichTextRun> {
         let bytes = content.as_bytes();
-        let mut text = String::new();
-        let mut search_start = 0;
-
-        while let Some(rel_pos) = memchr::memmem::find(&bytes[search_start..], b"<t") {
-            let t_pos = search_start + rel_pos;
-            let after_t = &bytes[t_pos..];
-            let gt_rel = match memchr::memchr(b'>', after_t) {
-                Some(p) => p,
-                None => break,
-            };
-            let text_start = t_pos + gt_rel + 1;
-
-            let after_text = &bytes[text_start..];
-            if let Some(end_rel) = memchr::memmem::find(after_text, b"</t>") {
-                let text_end = text_start + end_rel;
-                text.push_str(&content[text_start..text_end]);
-                search_start = text_end + 4;
-            } else {
-                break;
-            }
-        }
+        let text = Self::concat_t_elements(content);
 
         if text.is_empty() {
             return None;
@@ -287,3 +322,56 @@ impl SharedStrings {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufReader;
+
+    const SHARED_STRINGS: &str = concat!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>"#,
+        r#"<sst xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" count="4">"#,
+        "<si><t>Plain</t></si>",
+        r#"<si><r><rPr><b/><sz val="11"/></rPr><t>Bold</t></r><r><t xml:space="preserve"> tail</t></r></si>"#,
+        "<si><t>a > b</t></si>",
+        "<si><t>Ünïcødé</t></si>",
+        "<si><t/></si>",
+        "<si><t>Fish &amp; Chips</t></si>",
+        "</sst>"
+    );
+
+    #[test]
+    fn test_parse_reader_matches_parse() {
+        let expected = SharedStrings::parse(SHARED_STRINGS).unwrap();
+        // A tiny buffer forces tags and characters across read boundaries
+        let reader = BufReader::with_capacity(3, SHARED_STRINGS.as_bytes());
+        let streamed = SharedStrings::parse_reader(reader).unwrap();
+
+        assert_eq!(streamed.strings(), expected.strings());
+        assert_eq!(
+            streamed.strings(),
+            ["Plain", "Bold tail", "a > b", "Ünïcødé", "", "Fish & Chips"]
+        );
+        let runs = streamed.rich_text_runs(1).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert!(runs[0].bold);
+        assert_eq!(runs[0].font_size, Some(11.0));
+    }
+
+    #[test]
+    fn test_parse_reader_from_archive_stream() {
+        use soapberry_zip::office::{ArchiveReader, StreamingArchiveWriter};
+
+        let mut writer = StreamingArchiveWriter::new();
+        writer
+            .write_deflated("xl/sharedStrings.xml", SHARED_STRINGS.as_bytes())
+            .unwrap();
+        let bytes = writer.finish_to_bytes().unwrap();
+
+        let archive = ArchiveReader::new(&bytes).unwrap();
+        let stream = archive.read_stream("xl/sharedStrings.xml").unwrap();
+        let table = SharedStrings::parse_reader(BufReader::new(stream)).unwrap();
+        assert_eq!(table.len(), 6);
+        assert_eq!(table.get(2), Some("a > b"));
+    }
+}
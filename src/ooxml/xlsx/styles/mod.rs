@@ -45,10 +45,12 @@ pub use cell_style::CellStyle;
 pub use fill::Fill;
 pub use font::Font;
 pub use number_format::NumberFormat;
+pub(crate) use number_format::builtin_format_id;
 
 use std::collections::HashMap;
 
 use crate::ooxml::error::Result;
+use crate::sheet::date_system::is_date_format_id;
 
 /// Styles collection for an Excel workbook.
 ///
@@ -128,6 +130,17 @@ impl Styles {
         self.cell_xfs.get(id)
     }
 
+    /// Check if the cell format at index `id` shows numbers as dates or
+    /// times, including through a built-in date format.
+    pub fn is_date_style(&self, id: usize) -> bool {
+        self.get_cell_style(id)
+            .and_then(|style| style.num_fmt_id)
+            .is_some_and(|num_fmt_id| {
+                let code = self.get_number_format(num_fmt_id).map(|f| f.code.as_str());
+                is_date_format_id(num_fmt_id, code)
+            })
+    }
+
     /// Get the number of fonts defined.
     #[inline]
     pub fn font_count(&self) -> usize {
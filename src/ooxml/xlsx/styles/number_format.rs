@@ -38,47 +38,13 @@ impl NumberFormat {
 ///
 /// This function uses the same logic as the internal `detect_custom_number_format`.
 pub fn is_date_format(format: &str) -> bool {
-    let mut escaped = false;
-    let mut is_quote = false;
-    let mut brackets = 0u8;
-    let mut prev = ' ';
-    let mut hms = false;
-    let mut ap = false;
-
-    for s in format.chars() {
-        match (s, escaped, is_quote, ap, brackets) {
-            (_, true, ..) => escaped = false, // if escaped, ignore
-            ('_' | '\\', ..) => escaped = true,
-            ('"', _, true, _, _) => is_quote = false,
-            (_, _, true, _, _) => (), // inside quotes, skip
-            ('"', _, _, _, _) => is_quote = true,
-            (';', ..) => return false, // first format only
-            ('[', ..) => brackets += 1,
-            (']', .., 1) if hms => return false, // TimeDelta, not DateTime
-            (']', ..) => brackets = brackets.saturating_sub(1),
-            ('a' | 'A', _, _, false, 0) => ap = true,
-            ('p' | 'm' | '/' | 'P' | 'M', _, _, true, 0) => return true,
-            ('d' | 'm' | 'h' | 'y' | 's' | 'D' | 'M' | 'H' | 'Y' | 'S', _, _, false, 0) => {
-                return true;
-            },
-            _ => {
-                if hms && s.eq_ignore_ascii_case(&prev) {
-                    // ok ...
-                } else {
-                    hms = prev == '[' && matches!(s, 'm' | 'h' | 's' | 'M' | 'H' | 'S');
-                }
-            },
-        }
-        prev = s;
-    }
-    false
+    crate::sheet::date_system::is_date_format_code(format)
 }
 
 /// Get the format code for a built-in number format ID.
 ///
 /// Returns `None` if the ID is not a recognized built-in format.
 /// Built-in formats are Excel's standard formats (0-163).
-#[allow(dead_code)] // Reserved for future use
 pub(crate) fn builtin_format_code(id: u32) -> Option<&'static str> {
     match id {
         0 => Some("General"),
@@ -113,6 +79,13 @@ pub(crate) fn builtin_format_code(id: u32) -> Option<&'static str> {
     }
 }
 
+/// Get the built-in number format ID for a format code.
+///
+/// Returns `None` if the code is not one of Excel's built-in formats.
+pub(crate) fn builtin_format_id(code: &str) -> Option<u32> {
+    (0..=49).find(|&id| builtin_format_code(id) == Some(code))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,6 +174,14 @@ mod tests {
         assert!(!is_date_format("0.00;-0.00"));
     }
 
+    #[test]
+    fn test_builtin_format_id() {
+        assert_eq!(builtin_format_id("General"), Some(0));
+        assert_eq!(builtin_format_id("0.00%"), Some(10));
+        assert_eq!(builtin_format_id("@"), Some(49));
+        assert_eq!(builtin_format_id("0.0%"), None);
+    }
+
     #[test]
     fn test_builtin_format_code() {
         assert_eq!(builtin_format_code(0), Some("General"));
@@ -441,7 +441,12 @@ fn parse_cell_xfs(reader: &mut Reader<&[u8]>, cell_xfs: &mut Vec<CellStyle>) ->
     loop {
         match reader.read_event() {
             Ok(Event::Start(e)) if e.local_name().as_ref() == b"xf" => {
-                let style = parse_xf(reader, &e)?;
+                let style = parse_xf(reader, &e, true)?;
+                cell_xfs.push(style);
+            },
+            // Self-closing <xf .../> has no alignment or protection children
+            Ok(Event::Empty(e)) if e.local_name().as_ref() == b"xf" => {
+                let style = parse_xf(reader, &e, false)?;
                 cell_xfs.push(style);
             },
             Ok(Event::End(e))
@@ -463,6 +468,7 @@ fn parse_cell_xfs(reader: &mut Reader<&[u8]>, cell_xfs: &mut Vec<CellStyle>) ->
 fn parse_xf(
     reader: &mut Reader<&[u8]>,
     start: &quick_xml::events::BytesStart,
+    has_children: bool,
 ) -> Result<CellStyle> {
     let mut style = CellStyle::new();
 
@@ -528,6 +534,10 @@ fn parse_xf(
         }
     }
 
+    if !has_children {
+        return Ok(style);
+    }
+
     // Parse child elements
     loop {
         match reader.read_event() {
@@ -608,6 +618,8 @@ fn parse_color(
     reader: &mut Reader<&[u8]>,
     start: &quick_xml::events::BytesStart,
 ) -> Result<Option<String>> {
+    let mut theme = None;
+    let mut tint = None;
     for attr in start.attributes().flatten() {
         match attr.key.local_name().as_ref() {
             b"rgb" => {
@@ -617,9 +629,12 @@ fn parse_color(
             },
             b"theme" => {
                 if let Ok(value) = attr.decode_and_unescape_value(reader.decoder()) {
-                    // For now, just store theme reference as-is
-                    // A full implementation would resolve theme colors
-                    return Ok(Some(format!("theme:{}", value)));
+                    theme = Some(value.into_owned());
+                }
+            },
+            b"tint" => {
+                if let Ok(value) = attr.decode_and_unescape_value(reader.decoder()) {
+                    tint = value.parse::<f64>().ok().filter(|&t| t != 0.0);
                 }
             },
             b"indexed" => {
@@ -634,5 +649,38 @@ fn parse_color(
         }
     }
 
-    Ok(None)
+    // Theme references keep their tint so they can be resolved against the
+    // workbook theme (see `ColorSpec::from_spreadsheet`)
+    Ok(theme.map(|theme| match tint {
+        Some(tint) => format!("theme:{};tint:{}", theme, tint),
+        None => format!("theme:{}", theme),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_self_closing_and_nested_xfs() {
+        let xml = r#"<styleSheet xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main">
+            <numFmts count="1"><numFmt numFmtId="164" formatCode="yyyy-mm-dd"/></numFmts>
+            <cellXfs count="3">
+                <xf numFmtId="0" fontId="0" fillId="0" borderId="0"/>
+                <xf numFmtId="164" fontId="0" fillId="0" borderId="0" applyNumberFormat="1"/>
+                <xf numFmtId="0" fontId="1" fillId="0" borderId="0"><alignment horizontal="center"/></xf>
+            </cellXfs>
+        </styleSheet>"#;
+
+        let styles = parse_styles(xml).unwrap();
+        assert_eq!(styles.get_cell_style(1).unwrap().num_fmt_id, Some(164));
+        assert!(styles.get_cell_style(1).unwrap().apply_number_format);
+        let centered = styles.get_cell_style(2).unwrap();
+        assert_eq!(centered.font_id, Some(1));
+        assert_eq!(
+            centered.alignment.as_ref().unwrap().horizontal.as_deref(),
+            Some("center")
+        );
+        assert!(styles.get_cell_style(3).is_none());
+    }
 }
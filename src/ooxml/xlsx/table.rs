@@ -2,7 +2,9 @@
 //!
 //! Tables in Excel provide structured references and enhanced formatting for data ranges.
 
+use crate::common::xml::unescape_xml;
 use crate::ooxml::xlsx::sort::SortState;
+use crate::ooxml::xlsx::worksheet::Worksheet;
 
 /// Table style information for visual formatting.
 #[derive(Debug, Clone)]
@@ -108,6 +110,24 @@ impl TotalsRowFunction {
             _ => None,
         }
     }
+
+    /// `SUBTOTAL` function number for this aggregate, ignoring hidden rows.
+    ///
+    /// Returns `None` for [`Custom`](Self::Custom), whose formula is stored
+    /// in the column's `totals_row_formula`.
+    pub fn subtotal_code(&self) -> Option<u32> {
+        match self {
+            Self::Average => Some(101),
+            Self::CountNums => Some(102),
+            Self::Count => Some(103),
+            Self::Max => Some(104),
+            Self::Min => Some(105),
+            Self::StdDev => Some(107),
+            Self::Sum => Some(109),
+            Self::Var => Some(110),
+            Self::Custom => None,
+        }
+    }
 }
 
 /// A formula for a table column (calculated or totals row).
@@ -263,6 +283,138 @@ impl Table {
     pub fn column_names(&self) -> Vec<&str> {
         self.columns.iter().map(|c| c.name.as_str()).collect()
     }
+
+    /// Bounds of the table range as 1-based
+    /// `(first_row, first_col, last_row, last_col)`.
+    pub fn range_bounds(&self) -> Option<(u32, u32, u32, u32)> {
+        parse_range(&self.ref_range)
+            .map(|(min_col, min_row, max_col, max_row)| (min_row, min_col, max_row, max_col))
+    }
+
+    /// Whether the first row of the range holds the column headers.
+    pub fn has_header_row(&self) -> bool {
+        self.header_row_count != Some(0)
+    }
+
+    /// Whether the last row of the range is a totals row.
+    pub fn has_totals_row(&self) -> bool {
+        self.totals_row_count.unwrap_or(0) > 0
+    }
+
+    /// Parse a table part (`xl/tables/tableN.xml`).
+    ///
+    /// Returns `None` if the `<table>` element or its `id`, `name` or `ref`
+    /// attribute is missing.
+    pub fn parse(xml: &str) -> Option<Self> {
+        let table_start = xml.find("<table ")?;
+        let tag_end = table_start + xml[table_start..].find('>')?;
+        let tag = &xml[table_start..tag_end];
+
+        let attr = |name: &str| extract_attribute(tag, name);
+        let flag = |name: &str| attr(name).map(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+        let count = |name: &str| attr(name).and_then(|v| v.parse::<u32>().ok());
+
+        let id = count("id")?;
+        let name = attr("name")?;
+        let ref_range = attr("ref")?;
+        let mut table = Table {
+            id,
+            display_name: attr("displayName").unwrap_or_else(|| name.clone()),
+            name,
+            comment: attr("comment"),
+            ref_range,
+            table_type: attr("tableType").as_deref().and_then(TableType::parse),
+            header_row_count: count("headerRowCount"),
+            totals_row_count: count("totalsRowCount"),
+            totals_row_shown: flag("totalsRowShown"),
+            published: flag("published"),
+            columns: Vec::new(),
+            auto_filter_range: None,
+            sort_state: None,
+            style_info: None,
+        };
+
+        let body = &xml[tag_end..];
+        if let Some(start) = body.find("<autoFilter ") {
+            let end = body[start..].find('>').map_or(body.len(), |e| start + e);
+            table.auto_filter_range = extract_attribute(&body[start..end], "ref");
+        }
+
+        if let Some(start) = body.find("<sortState") {
+            let rest = &body[start..];
+            let end = rest
+                .find("</sortState>")
+                .map(|e| e + "</sortState>".len())
+                .or_else(|| rest.find("/>").map(|e| e + 2))
+                .unwrap_or(rest.len());
+            table.sort_state = Worksheet::parse_sort_state(&rest[..end], Some(&table.ref_range));
+        }
+
+        let mut pos = 0;
+        while let Some(start) = body[pos..].find("<tableColumn ") {
+            let start = pos + start;
+            let Some(tag_len) = body[start..].find('>') else {
+                break;
+            };
+            let tag = &body[start..start + tag_len];
+            let (inner, next) = if tag.ends_with('/') {
+                ("", start + tag_len + 1)
+            } else {
+                let inner_start = start + tag_len + 1;
+                let inner_len = body[inner_start..]
+                    .find("</tableColumn>")
+                    .unwrap_or(body.len() - inner_start);
+                (
+                    &body[inner_start..inner_start + inner_len],
+                    inner_start + inner_len,
+                )
+            };
+            if let Some(column) = parse_table_column(tag, inner) {
+                table.columns.push(column);
+            }
+            pos = next;
+        }
+
+        if let Some(start) = body.find("<tableStyleInfo") {
+            let end = body[start..].find('>').map_or(body.len(), |e| start + e);
+            table.style_info = TableStyleInfo::parse(&body[start..end]);
+        }
+
+        Some(table)
+    }
+}
+
+/// Parse a `<tableColumn>` start tag and its inner XML.
+fn parse_table_column(tag: &str, inner: &str) -> Option<TableColumn> {
+    let id = extract_attribute(tag, "id")?.parse::<u32>().ok()?;
+    let mut column = TableColumn::new(id, extract_attribute(tag, "name")?);
+    column.unique_name = extract_attribute(tag, "uniqueName");
+    column.totals_row_function =
+        extract_attribute(tag, "totalsRowFunction").and_then(|f| TotalsRowFunction::parse(&f));
+    column.totals_row_label = extract_attribute(tag, "totalsRowLabel");
+    column.calculated_column_formula = parse_table_formula(inner, "calculatedColumnFormula");
+    column.totals_row_formula = parse_table_formula(inner, "totalsRowFormula");
+    Some(column)
+}
+
+/// Parse a `<calculatedColumnFormula>` or `<totalsRowFormula>` element.
+fn parse_table_formula(inner: &str, element: &str) -> Option<TableFormula> {
+    let start = inner.find(&format!("<{element}"))?;
+    let tag_end = start + inner[start..].find('>')?;
+    let tag = &inner[start..tag_end];
+    let text_end = tag_end + inner[tag_end..].find(&format!("</{element}>"))?;
+    Some(TableFormula {
+        array: extract_attribute(tag, "array").map(|v| v == "1" || v.eq_ignore_ascii_case("true")),
+        text: unescape_xml(&inner[tag_end + 1..text_end]),
+    })
+}
+
+/// Extract and unescape an attribute value from a start tag.
+fn extract_attribute(tag: &str, attr: &str) -> Option<String> {
+    let search_str = format!(" {}=\"", attr);
+    let start = tag.find(&search_str)? + search_str.len();
+    let end = tag[start..].find('"')? + start;
+    Some(unescape_xml(&tag[start..end]))
 }
 
 /// Parse a cell range like "A1:D10" into (min_col, min_row, max_col, max_row).
@@ -300,6 +452,45 @@ fn parse_cell_ref(cell_ref: &str) -> Option<(u32, u32)> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_table_parse() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<table xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" id="3" name="Sales" displayName="Sales_2" ref="A1:C5" totalsRowCount="1">
+<autoFilter ref="A1:C4"/>
+<tableColumns count="3">
+<tableColumn id="1" name="Region" totalsRowLabel="Total"/>
+<tableColumn id="2" name="Q1 &amp; Q2" totalsRowFunction="sum"/>
+<tableColumn id="3" name="Share"><calculatedColumnFormula>Sales[[#This Row],[Q1 &amp; Q2]]/100</calculatedColumnFormula></tableColumn>
+</tableColumns>
+<tableStyleInfo name="TableStyleLight9" showFirstColumn="0" showLastColumn="0" showRowStripes="1" showColumnStripes="0"/>
+</table>"#;
+        let table = Table::parse(xml).unwrap();
+        assert_eq!(table.id, 3);
+        assert_eq!(table.name, "Sales");
+        assert_eq!(table.display_name, "Sales_2");
+        assert_eq!(table.ref_range, "A1:C5");
+        assert_eq!(table.range_bounds(), Some((1, 1, 5, 3)));
+        assert!(table.has_header_row());
+        assert!(table.has_totals_row());
+        assert_eq!(table.auto_filter_range.as_deref(), Some("A1:C4"));
+        assert_eq!(table.column_names(), ["Region", "Q1 & Q2", "Share"]);
+        assert_eq!(table.columns[0].totals_row_label.as_deref(), Some("Total"));
+        assert_eq!(
+            table.columns[1].totals_row_function,
+            Some(TotalsRowFunction::Sum)
+        );
+        assert_eq!(
+            table.columns[2]
+                .calculated_column_formula
+                .as_ref()
+                .map(|f| f.text.as_str()),
+            Some("Sales[[#This Row],[Q1 & Q2]]/100")
+        );
+        let style = table.style_info.unwrap();
+        assert_eq!(style.name.as_deref(), Some("TableStyleLight9"));
+        assert_eq!(style.show_row_stripes, Some(true));
+    }
+
     #[test]
     fn test_table_style_info_new() {
         let style = TableStyleInfo::new();
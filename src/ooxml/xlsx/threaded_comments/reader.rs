@@ -1,5 +1,6 @@
 //! Reader module for threaded comments XML parsing.
 
+use crate::common::xml::unescape_xml;
 use crate::ooxml::opc::constants::relationship_type as rt;
 use crate::ooxml::opc::{OpcPackage, PackURI};
 use crate::sheet::Result as SheetResult;
@@ -22,8 +23,10 @@ pub fn read_persons(package: &OpcPackage) -> SheetResult<Option<PersonList>> {
         }
 
         let persons_uri = rel.target_partname()?;
-        let persons_part = package.get_part(&persons_uri)?;
-        let xml = std::str::from_utf8(persons_part.blob())?;
+        let Ok(persons_part) = package.get_part(&persons_uri) else {
+            continue;
+        };
+        let xml = std::str::from_utf8(persons_part.try_blob()?)?;
 
         return parse_person_list(xml);
     }
@@ -48,8 +51,10 @@ pub fn read_threaded_comments(
         }
 
         let comments_uri = rel.target_partname()?;
-        let comments_part = package.get_part(&comments_uri)?;
-        let xml = std::str::from_utf8(comments_part.blob())?;
+        let Ok(comments_part) = package.get_part(&comments_uri) else {
+            continue;
+        };
+        let xml = std::str::from_utf8(comments_part.try_blob()?)?;
 
         return parse_threaded_comments(xml);
     }
@@ -170,7 +175,7 @@ fn extract_text_element(xml: &str) -> Option<String> {
     let text_start = xml.find("<text>")?;
     let after_start = &xml[text_start + "<text>".len()..];
     let text_end = after_start.find("</text>")?;
-    Some(after_start[..text_end].to_string())
+    Some(unescape_xml(&after_start[..text_end]))
 }
 
 /// Parse mentions from XML.
@@ -232,5 +237,5 @@ fn extract_attr(tag: &str, attr_name: &str) -> Option<String> {
     let start = tag.find(&pattern)? + pattern.len();
     let after_start = &tag[start..];
     let end = after_start.find('"')?;
-    Some(after_start[..end].to_string())
+    Some(unescape_xml(&after_start[..end]))
 }
@@ -1,7 +1,8 @@
 //! Worksheet view definitions for Excel.
 //!
 //! This module provides data structures for worksheet view settings such as
-//! zoom, right-to-left mode, and the active view type.
+//! zoom, right-to-left mode, the active view type, frozen or split panes and
+//! the cell selection.
 
 /// Worksheet view type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -33,8 +34,141 @@ impl SheetViewType {
     }
 }
 
+/// One of the (up to four) panes of a split or frozen window.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneType {
+    /// Bottom right pane, present when splitting both ways.
+    BottomRight,
+    /// Top right pane, present when splitting vertically.
+    TopRight,
+    /// Bottom left pane, present when splitting horizontally.
+    BottomLeft,
+    /// Top left pane, always present.
+    TopLeft,
+}
+
+impl PaneType {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::BottomRight => "bottomRight",
+            Self::TopRight => "topRight",
+            Self::BottomLeft => "bottomLeft",
+            Self::TopLeft => "topLeft",
+        }
+    }
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "bottomRight" => Some(Self::BottomRight),
+            "topRight" => Some(Self::TopRight),
+            "bottomLeft" => Some(Self::BottomLeft),
+            "topLeft" => Some(Self::TopLeft),
+            _ => None,
+        }
+    }
+}
+
+/// Whether panes are frozen or merely split.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaneState {
+    /// Panes are split but not frozen; splits are measured in twips.
+    Split,
+    /// Panes are frozen; splits are measured in rows and columns.
+    Frozen,
+    /// Panes are frozen and split when unfrozen.
+    FrozenSplit,
+}
+
+impl PaneState {
+    pub(crate) fn as_str(self) -> &'static str {
+        match self {
+            Self::Split => "split",
+            Self::Frozen => "frozen",
+            Self::FrozenSplit => "frozenSplit",
+        }
+    }
+
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        match value {
+            "split" => Some(Self::Split),
+            "frozen" => Some(Self::Frozen),
+            "frozenSplit" => Some(Self::FrozenSplit),
+            _ => None,
+        }
+    }
+}
+
+/// Frozen or split pane configuration of a sheet view.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Pane {
+    /// Horizontal split position: columns when frozen, twips when split.
+    pub x_split: f64,
+    /// Vertical split position: rows when frozen, twips when split.
+    pub y_split: f64,
+    /// Top-left visible cell of the bottom right pane.
+    pub top_left_cell: Option<String>,
+    /// Pane that has the focus.
+    pub active_pane: Option<PaneType>,
+    /// Split state; `None` means split.
+    pub state: Option<PaneState>,
+}
+
+impl Pane {
+    /// Panes frozen above `rows` rows and left of `cols` columns.
+    ///
+    /// Returns `None` when nothing is frozen.
+    pub fn frozen(rows: u32, cols: u32) -> Option<Self> {
+        let active_pane = match (cols > 0, rows > 0) {
+            (true, true) => PaneType::BottomRight,
+            (true, false) => PaneType::TopRight,
+            (false, true) => PaneType::BottomLeft,
+            (false, false) => return None,
+        };
+        Some(Self {
+            x_split: cols as f64,
+            y_split: rows as f64,
+            top_left_cell: Some(cell_reference(rows + 1, cols + 1)),
+            active_pane: Some(active_pane),
+            state: Some(PaneState::Frozen),
+        })
+    }
+
+    /// Whether the panes are frozen rather than split.
+    pub fn is_frozen(&self) -> bool {
+        matches!(self.state, Some(PaneState::Frozen | PaneState::FrozenSplit))
+    }
+
+    /// Number of frozen `(rows, columns)`, if the panes are frozen.
+    pub fn frozen_rows_cols(&self) -> Option<(u32, u32)> {
+        self.is_frozen()
+            .then(|| (self.y_split.max(0.0) as u32, self.x_split.max(0.0) as u32))
+    }
+}
+
+/// Selected cells in one pane of a sheet view.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Selection {
+    /// Pane the selection belongs to; `None` means the top left pane.
+    pub pane: Option<PaneType>,
+    /// Active cell, e.g. `"B2"`.
+    pub active_cell: Option<String>,
+    /// Selected ranges, space separated (e.g. `"B2:C4 E1"`).
+    pub sqref: Option<String>,
+}
+
+impl Selection {
+    /// Select a single cell in the given pane.
+    pub fn cell(pane: Option<PaneType>, cell: &str) -> Self {
+        Self {
+            pane,
+            active_cell: Some(cell.to_string()),
+            sqref: Some(cell.to_string()),
+        }
+    }
+}
+
 /// Worksheet view configuration.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct SheetView {
     /// Show formulas instead of values.
     pub show_formulas: Option<bool>,
@@ -54,4 +188,51 @@ pub struct SheetView {
     pub zoom_scale: Option<u16>,
     /// Zoom scale for normal view.
     pub zoom_scale_normal: Option<u16>,
+    /// Frozen or split panes.
+    pub pane: Option<Pane>,
+    /// Selections, at most one per pane.
+    pub selections: Vec<Selection>,
+}
+
+/// Format a 1-based row and column as an A1 reference.
+fn cell_reference(row: u32, col: u32) -> String {
+    let mut letters = Vec::new();
+    let mut n = col;
+    while n > 0 {
+        n -= 1;
+        letters.push(b'A' + (n % 26) as u8);
+        n /= 26;
+    }
+    letters.reverse();
+    format!("{}{}", String::from_utf8_lossy(&letters), row)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frozen_pane_positions() {
+        let pane = Pane::frozen(1, 0).unwrap();
+        assert_eq!(pane.top_left_cell.as_deref(), Some("A2"));
+        assert_eq!(pane.active_pane, Some(PaneType::BottomLeft));
+        assert_eq!(pane.frozen_rows_cols(), Some((1, 0)));
+
+        let pane = Pane::frozen(2, 27).unwrap();
+        assert_eq!(pane.top_left_cell.as_deref(), Some("AB3"));
+        assert_eq!(pane.active_pane, Some(PaneType::BottomRight));
+
+        assert!(Pane::frozen(0, 0).is_none());
+    }
+
+    #[test]
+    fn split_panes_are_not_frozen() {
+        let pane = Pane {
+            x_split: 2400.0,
+            y_split: 1200.0,
+            ..Default::default()
+        };
+        assert!(!pane.is_frozen());
+        assert_eq!(pane.frozen_rows_cols(), None);
+    }
 }
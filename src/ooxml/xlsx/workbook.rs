@@ -3,9 +3,16 @@
 //! This module provides the concrete implementation of the Workbook trait
 //! for Excel (.xlsx) files using the Office Open XML format.
 
+use crate::common::{Metadata, Protection};
+use crate::ooxml::charts::reader::read_related_charts;
+use crate::ooxml::charts::{Chart, Series, TitleText};
 use crate::ooxml::common::DocumentProperties;
+use crate::ooxml::common::properties::write_property_part;
+use crate::ooxml::custom_properties::CustomProperties;
+use crate::ooxml::drawings::color::ColorScheme;
 use crate::ooxml::opc::{OpcPackage, PackURI};
 use crate::ooxml::pivot::PivotTable;
+use crate::ooxml::xlsx::pivot::PivotTableView;
 use crate::ooxml::xlsx::writer::workbook::{
     generate_pivot_cache_definition_xml, generate_pivot_cache_records_xml,
     generate_pivot_table_definition_xml, render_pivot_table_sheet_cells,
@@ -13,11 +20,14 @@ use crate::ooxml::xlsx::writer::workbook::{
 use crate::ooxml::xlsx::writer::{MutableWorkbookData, MutableWorksheet};
 use crate::ooxml::xlsx::{SharedStrings, Styles};
 use crate::sheet::{
-    Result as SheetResult, WorkbookTrait, Worksheet as WorksheetTrait, WorksheetIterator,
+    CellValue, DateSystem, Result as SheetResult, WorkbookTrait, Worksheet as WorksheetTrait,
+    WorksheetIterator,
 };
 use std::collections::HashMap;
 
+use super::cell::Cell;
 use super::parsers::workbook_parser;
+use super::rows::RowsIter;
 use super::worksheet::{Worksheet, WorksheetInfo, WorksheetIterator as XlsxWorksheetIterator};
 
 /// Concrete implementation of a Workbook for Excel files.
@@ -35,10 +45,14 @@ pub struct Workbook {
     shared_strings: SharedStrings,
     /// Styles information
     styles: Styles,
+    /// Theme color scheme, used to resolve theme colors in styles
+    theme_colors: ColorScheme,
     /// Mutable workbook data for writing (cached)
     mutable_data: Option<MutableWorkbookData>,
     /// Document properties (metadata)
     properties: DocumentProperties,
+    /// Custom document properties
+    custom_properties: CustomProperties,
     /// Whether the workbook uses the 1904 date system
     is_1904_date_system: bool,
 }
@@ -151,11 +165,19 @@ impl Workbook {
         package.relate_to("docProps/app.xml", rt::EXTENDED_PROPERTIES);
         package.add_part(Box::new(app_props_part));
 
-        Self::new(package)
+        // Start from empty properties rather than the template's placeholders
+        let mut workbook = Self::new(package)?;
+        workbook.properties = DocumentProperties::new();
+        Ok(workbook)
     }
 
     /// Create a new workbook from an OPC package.
     pub fn new(package: OpcPackage) -> SheetResult<Self> {
+        let properties = DocumentProperties::from_package(&package);
+        let custom_properties =
+            crate::ooxml::custom_properties::extract_custom_properties(&package)
+                .unwrap_or_else(|_| CustomProperties::new());
+
         let mut workbook = Workbook {
             package,
             worksheets: Vec::new(),
@@ -163,14 +185,17 @@ impl Workbook {
             active_sheet_index: 0,
             shared_strings: SharedStrings::new(),
             styles: Styles::new(),
+            theme_colors: ColorScheme::default(),
             mutable_data: Some(MutableWorkbookData::new()),
-            properties: DocumentProperties::new(),
+            properties,
+            custom_properties,
             is_1904_date_system: false,
         };
 
         workbook.load_workbook_info()?;
         workbook.load_shared_strings()?;
         workbook.load_styles()?;
+        workbook.load_theme()?;
         workbook.load_print_settings()?;
 
         Ok(workbook)
@@ -182,7 +207,7 @@ impl Workbook {
         let workbook_part = self.package.get_part(&workbook_uri)?;
 
         // Parse the workbook XML to extract sheet information
-        let content = std::str::from_utf8(workbook_part.blob())?;
+        let content = std::str::from_utf8(workbook_part.try_blob()?)?;
 
         // Extract sheets from workbook.xml
         let (worksheets, active_sheet_index, uses_1904_date_system) =
@@ -198,11 +223,18 @@ impl Workbook {
     }
 
     /// Load shared strings from xl/sharedStrings.xml
+    ///
+    /// A table that is still in the package file is parsed while it is
+    /// decompressed, so it is never held in memory as XML.
     fn load_shared_strings(&mut self) -> SheetResult<()> {
         let shared_strings_uri = PackURI::new("/xl/sharedStrings.xml")?;
         if let Ok(shared_strings_part) = self.package.get_part(&shared_strings_uri) {
-            let content = std::str::from_utf8(shared_strings_part.blob())?;
-            self.shared_strings = SharedStrings::parse(content)?;
+            self.shared_strings = if shared_strings_part.is_deferred() {
+                let reader = std::io::BufReader::new(shared_strings_part.content_reader()?);
+                SharedStrings::parse_reader(reader)?
+            } else {
+                SharedStrings::parse(std::str::from_utf8(shared_strings_part.try_blob()?)?)?
+            };
         }
 
         Ok(())
@@ -212,13 +244,23 @@ impl Workbook {
     fn load_styles(&mut self) -> SheetResult<()> {
         let styles_uri = PackURI::new("/xl/styles.xml")?;
         if let Ok(styles_part) = self.package.get_part(&styles_uri) {
-            let content = std::str::from_utf8(styles_part.blob())?;
+            let content = std::str::from_utf8(styles_part.try_blob()?)?;
             self.styles = Styles::parse(content)
                 .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
         }
         Ok(())
     }
 
+    /// Load the theme color scheme from xl/theme/theme1.xml
+    fn load_theme(&mut self) -> SheetResult<()> {
+        let theme_uri = PackURI::new("/xl/theme/theme1.xml")?;
+        if let Ok(theme_part) = self.package.get_part(&theme_uri) {
+            self.theme_colors = ColorScheme::from_theme_xml(theme_part.try_blob()?)
+                .map_err(|e| -> Box<dyn std::error::Error + Send + Sync> { Box::new(e) })?;
+        }
+        Ok(())
+    }
+
     /// Load worksheet print settings (print area, repeating rows/columns)
     /// from workbook-level defined names.
     fn load_print_settings(&mut self) -> SheetResult<()> {
@@ -230,7 +272,7 @@ impl Workbook {
             Err(_) => return Ok(()),
         };
 
-        let content = std::str::from_utf8(workbook_part.blob())?;
+        let content = std::str::from_utf8(workbook_part.try_blob()?)?;
 
         // Find the <definedNames> section if present.
         let start = if let Some(pos) = content.find("<definedNames>") {
@@ -388,7 +430,10 @@ impl Workbook {
     /// Get a worksheet by index
     fn get_worksheet(&self, index: usize) -> SheetResult<Worksheet<'_>> {
         if index >= self.worksheets.len() {
-            return Err("Worksheet index out of bounds".into());
+            return Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
+                index
+            ))));
         }
 
         let info = &self.worksheets[index];
@@ -400,6 +445,86 @@ impl Workbook {
         Ok(worksheet)
     }
 
+    /// Get a worksheet by index with access to XLSX-specific features.
+    ///
+    /// Unlike [`WorkbookTrait::worksheet_by_index`], this returns the concrete
+    /// [`Worksheet`] type, which exposes comments, hyperlinks, conditional
+    /// formatting and other details not covered by the generic trait.
+    pub fn worksheet(&self, index: usize) -> SheetResult<Worksheet<'_>> {
+        self.get_worksheet(index)
+    }
+
+    /// Stream the rows of the worksheet at `index` without loading it.
+    ///
+    /// See [`Worksheet::rows_iter`] for details. Unlike
+    /// [`worksheet`](Self::worksheet), this never builds the sheet's cell map.
+    pub fn rows_iter(&self, index: usize) -> SheetResult<RowsIter<'_>> {
+        let info = self
+            .worksheets
+            .get(index)
+            .ok_or("Worksheet index out of bounds")?;
+        Worksheet::new(self, info.clone()).rows_iter()
+    }
+
+    /// Parse every worksheet concurrently, returning them in workbook order.
+    ///
+    /// Worksheet XML is parsed on the rayon thread pool. The shared strings
+    /// table and styles are parsed once when the workbook is opened and are
+    /// borrowed immutably by every worker, so nothing is re-parsed per sheet.
+    /// Fails with the first error encountered (in sheet order).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::xlsx::Workbook;
+    ///
+    /// let workbook = Workbook::open("large.xlsx")?;
+    /// for sheet in workbook.worksheets_par()? {
+    ///     println!("{}: {} cells", sheet.info().name, sheet.non_empty_cell_count());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn worksheets_par(&self) -> SheetResult<Vec<Worksheet<'_>>> {
+        use rayon::prelude::*;
+
+        (0..self.worksheets.len())
+            .into_par_iter()
+            .map(|index| self.get_worksheet(index))
+            .collect()
+    }
+
+    /// Get the editing protection of the workbook.
+    ///
+    /// Combines the read-only recommendation, password to modify and
+    /// structure lock of workbook.xml with the protection of every
+    /// worksheet. The "final" mark is a document property; see
+    /// [`Protection::from_metadata`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::xlsx::Workbook;
+    ///
+    /// let workbook = Workbook::open("workbook.xlsx")?;
+    /// let protection = workbook.protection()?;
+    /// println!("{:?}, password: {}", protection.kind, protection.has_password);
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn protection(&self) -> SheetResult<Protection> {
+        let workbook_part = self.package.get_part(&PackURI::new("/xl/workbook.xml")?)?;
+        let mut protection =
+            workbook_parser::parse_protection(std::str::from_utf8(workbook_part.try_blob()?)?);
+        for info in &self.worksheets {
+            let uri = PackURI::new(format!("/xl/worksheets/sheet{}.xml", info.sheet_id))?;
+            if let Ok(part) = self.package.get_part(&uri) {
+                protection = protection.or(workbook_parser::parse_protection(std::str::from_utf8(
+                    part.try_blob()?,
+                )?));
+            }
+        }
+        Ok(protection)
+    }
+
     /// Get the OPC package (for internal use by worksheet)
     pub(crate) fn package(&self) -> &OpcPackage {
         &self.package
@@ -414,6 +539,11 @@ impl Workbook {
     pub(crate) fn styles(&self) -> &Styles {
         &self.styles
     }
+
+    /// Get the date system of the workbook (for internal use by worksheet)
+    pub(crate) fn date_system(&self) -> DateSystem {
+        DateSystem::from_1904_flag(self.is_1904_date_system)
+    }
 }
 
 impl WorkbookTrait for Workbook {
@@ -434,7 +564,10 @@ impl WorkbookTrait for Workbook {
                 return Ok(Box::new(worksheet));
             }
         }
-        Err(format!("Worksheet '{}' not found", name).into())
+        Err(Box::new(crate::common::Error::InvalidReference(format!(
+            "Worksheet '{}' not found",
+            name
+        ))))
     }
 
     fn worksheet_by_index(&self, index: usize) -> SheetResult<Box<dyn WorksheetTrait + '_>> {
@@ -682,6 +815,13 @@ impl Workbook {
         &self.properties
     }
 
+    /// Get the theme color scheme used to resolve theme colors.
+    ///
+    /// Workbooks without a theme part use the default Office colors.
+    pub fn theme_colors(&self) -> &ColorScheme {
+        &self.theme_colors
+    }
+
     /// Get a mutable reference to the workbook properties.
     ///
     /// # Examples
@@ -699,6 +839,39 @@ impl Workbook {
         &mut self.properties
     }
 
+    /// Get a reference to the custom workbook properties.
+    pub fn custom_properties(&self) -> &CustomProperties {
+        &self.custom_properties
+    }
+
+    /// Get a mutable reference to the custom workbook properties.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::custom_properties::PropertyValue;
+    /// use litchi::ooxml::xlsx::Workbook;
+    ///
+    /// let mut wb = Workbook::create()?;
+    /// wb.custom_properties_mut()
+    ///     .add_property("Department", PropertyValue::String("Finance".to_string()));
+    /// wb.save("workbook.xlsx")?;
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn custom_properties_mut(&mut self) -> &mut CustomProperties {
+        &mut self.custom_properties
+    }
+
+    /// Replace the workbook properties and custom properties with `metadata`.
+    ///
+    /// Core fields go to docProps/core.xml, application/company/manager/template
+    /// to docProps/app.xml and the custom property map to docProps/custom.xml
+    /// when the workbook is saved.
+    pub fn set_metadata(&mut self, metadata: &Metadata) {
+        self.properties = DocumentProperties::from(metadata);
+        self.custom_properties = CustomProperties::from(&metadata.custom_properties);
+    }
+
     /// Set the person list for threaded comments.
     ///
     /// Persons are used to identify authors of threaded comments.
@@ -751,6 +924,33 @@ impl Workbook {
     /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
     /// ```
     pub fn save<P: AsRef<std::path::Path>>(&mut self, path: P) -> SheetResult<()> {
+        self.update_parts_for_save()?;
+        self.package.save(path)?;
+        Ok(())
+    }
+
+    /// Save the workbook to a stream.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::ooxml::xlsx::Workbook;
+    /// use std::io::Cursor;
+    ///
+    /// let mut workbook = Workbook::create()?;
+    /// let mut cursor = Cursor::new(Vec::new());
+    /// workbook.to_stream(&mut cursor)?;
+    /// assert!(!cursor.into_inner().is_empty());
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn to_stream<W: std::io::Write + std::io::Seek>(&mut self, writer: W) -> SheetResult<()> {
+        self.update_parts_for_save()?;
+        self.package.to_stream(writer)?;
+        Ok(())
+    }
+
+    /// Write pending worksheet edits and document properties into the package.
+    fn update_parts_for_save(&mut self) -> SheetResult<()> {
         // If we have mutable data, update the workbook parts
         let should_update = self
             .mutable_data
@@ -772,7 +972,9 @@ impl Workbook {
         // Update app properties (extended properties)
         self.update_app_properties()?;
 
-        self.package.save(path)?;
+        // Update custom properties
+        self.update_custom_properties()?;
+
         Ok(())
     }
 
@@ -874,6 +1076,9 @@ impl Workbook {
             Vec::new(),
         );
 
+        // Table headers write into cells, so they go before the styles
+        data.prepare_tables()?;
+
         // Build styles from all worksheets FIRST
         let (styles_builder, worksheet_style_indices) = data.build_styles()?;
 
@@ -1142,24 +1347,18 @@ impl Workbook {
 
                 let table_xml = serialize_table(table)?;
                 let table_uri = PackURI::new(format!("/xl/tables/table{}.xml", table.id))?;
-                let table_part = BlobPart::new(
-                    table_uri,
-                    "application/vnd.openxmlformats-officedocument.spreadsheetml.table+xml"
-                        .to_string(),
-                    table_xml.into_bytes(),
-                );
+                let table_part =
+                    BlobPart::new(table_uri, ct::SML_TABLE.to_string(), table_xml.into_bytes());
                 self.package.add_part(Box::new(table_part));
 
                 // Add relationship from worksheet to table and capture the ID
-                let rel_id = ws_part.relate_to(
-                    &format!("../tables/table{}.xml", table.id),
-                    "http://schemas.openxmlformats.org/officeDocument/2006/relationships/table",
-                );
+                let rel_id =
+                    ws_part.relate_to(&format!("../tables/table{}.xml", table.id), rt::TABLE);
                 table_rel_ids.push(rel_id);
             }
 
-            // Generate and add drawing XML for images if present
-            if let Some(drawing_xml) = ws.generate_drawing_xml()? {
+            // Generate and add drawing XML for images and charts if present
+            let drawing_rel_id = if let Some(drawing_xml) = ws.generate_drawing_xml()? {
                 let drawing_uri =
                     PackURI::new(format!("/xl/drawings/drawing{}.xml", ws.sheet_id()))?;
 
@@ -1212,8 +1411,10 @@ impl Workbook {
                     let chart_id = ws.sheet_id() * 1000 + (ws.images().len() + idx) as u32;
                     let chart_uri = PackURI::new(format!("/xl/charts/chart{}.xml", chart_id))?;
 
-                    // Generate chart XML
-                    let chart_xml = crate::ooxml::xlsx::chart::generate_chart_xml(&chart.chart)
+                    // Generate chart XML, with series references bound to the cells
+                    let mut chart_model = chart.chart.clone();
+                    bind_chart_to_cells(&mut chart_model, index, &data.worksheets);
+                    let chart_xml = crate::ooxml::xlsx::chart::generate_chart_xml(&chart_model)
                         .map_err(|e| format!("Failed to generate chart XML: {}", e))?;
 
                     let chart_part = BlobPart::new(
@@ -1233,12 +1434,15 @@ impl Workbook {
 
                 self.package.add_part(Box::new(drawing_part));
 
-                // Add relationship from worksheet to drawing
-                ws_part.relate_to(
+                // Add relationship from worksheet to drawing and capture the ID
+                let rel_id = ws_part.relate_to(
                     &format!("../drawings/drawing{}.xml", ws.sheet_id()),
                     "http://schemas.openxmlformats.org/officeDocument/2006/relationships/drawing",
                 );
-            }
+                Some(rel_id)
+            } else {
+                None
+            };
 
             let mut pivot_table_rel_ids: Vec<String> = Vec::new();
             if let Some(targets) = pivot_table_targets_per_sheet.get(index) {
@@ -1253,6 +1457,7 @@ impl Workbook {
                 &mut data.shared_strings,
                 &style_indices,
                 &hyperlink_rel_ids,
+                drawing_rel_id.as_deref(),
                 vml_rel_id.as_deref(),
                 Some(&pivot_table_rel_ids),
                 Some(&table_rel_ids),
@@ -1303,33 +1508,47 @@ impl Workbook {
     /// Update the core.xml properties part.
     fn update_core_properties(&mut self) -> SheetResult<()> {
         use crate::ooxml::opc::constants::content_type as ct;
-        use crate::ooxml::opc::part::BlobPart;
-
-        let core_uri = PackURI::new("/docProps/core.xml")?;
+        use crate::ooxml::opc::constants::relationship_type as rt;
 
-        // Generate XML from properties
+        self.properties.stamp();
         let xml = self.properties.to_xml();
+        write_property_part(
+            &mut self.package,
+            rt::CORE_PROPERTIES,
+            "/docProps/core.xml",
+            ct::OPC_CORE_PROPERTIES,
+            |_| Ok(xml),
+        )?;
+        Ok(())
+    }
 
-        // Create or update the core properties part
-        let core_part = BlobPart::new(
-            core_uri,
-            ct::OPC_CORE_PROPERTIES.to_string(),
-            xml.into_bytes(),
-        );
+    /// Update the custom.xml properties part.
+    fn update_custom_properties(&mut self) -> SheetResult<()> {
+        use crate::ooxml::opc::constants::content_type as ct;
+        use crate::ooxml::opc::constants::relationship_type as rt;
 
-        self.package.add_part(Box::new(core_part));
+        // Only create custom properties part if there are custom properties
+        if self.custom_properties.is_empty() {
+            return Ok(());
+        }
 
+        let xml = self.custom_properties.to_xml()?;
+        write_property_part(
+            &mut self.package,
+            rt::CUSTOM_PROPERTIES,
+            "/docProps/custom.xml",
+            ct::OFC_CUSTOM_PROPERTIES,
+            |_| Ok(xml),
+        )?;
         Ok(())
     }
 
     /// Update the app.xml properties part with current worksheet information.
     fn update_app_properties(&mut self) -> SheetResult<()> {
         use crate::ooxml::opc::constants::content_type as ct;
-        use crate::ooxml::opc::part::BlobPart;
+        use crate::ooxml::opc::constants::relationship_type as rt;
         use std::fmt::Write;
 
-        let app_uri = PackURI::new("/docProps/app.xml")?;
-
         // Get worksheet names from mutable_data if available, otherwise from package
         let worksheet_names: Vec<String> = if let Some(ref data) = self.mutable_data {
             data.worksheets
@@ -1396,15 +1615,15 @@ impl Workbook {
         xml.push_str("<AppVersion>14.0000</AppVersion>");
         xml.push_str("</Properties>");
 
-        // Create or update the app properties part
-        let app_part = BlobPart::new(
-            app_uri,
-            ct::OFC_EXTENDED_PROPERTIES.to_string(),
-            xml.into_bytes(),
-        );
-
-        self.package.add_part(Box::new(app_part));
-
+        // Create or update the app properties part, with company/manager etc.
+        let properties = &self.properties;
+        write_property_part(
+            &mut self.package,
+            rt::EXTENDED_PROPERTIES,
+            "/docProps/app.xml",
+            ct::OFC_EXTENDED_PROPERTIES,
+            |_| properties.merge_app_xml(&xml),
+        )?;
         Ok(())
     }
 
@@ -1427,7 +1646,10 @@ impl Workbook {
     /// ```
     pub fn hide_sheet(&mut self, index: usize) -> SheetResult<()> {
         if index >= self.worksheets.len() {
-            return Err("Worksheet index out of bounds".into());
+            return Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
+                index
+            ))));
         }
 
         if self.mutable_data.is_none() {
@@ -1444,7 +1666,10 @@ impl Workbook {
     /// * `index` - Worksheet index (0-based)
     pub fn unhide_sheet(&mut self, index: usize) -> SheetResult<()> {
         if index >= self.worksheets.len() {
-            return Err("Worksheet index out of bounds".into());
+            return Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
+                index
+            ))));
         }
 
         if self.mutable_data.is_none() {
@@ -1486,7 +1711,10 @@ impl Workbook {
     /// ```
     pub fn move_sheet(&mut self, from_index: usize, to_index: usize) -> SheetResult<()> {
         if from_index >= self.worksheets.len() || to_index >= self.worksheets.len() {
-            return Err("Worksheet index out of bounds".into());
+            return Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
+                from_index.max(to_index)
+            ))));
         }
 
         if self.mutable_data.is_none() {
@@ -1523,7 +1751,10 @@ impl Workbook {
     /// ```
     pub fn set_sheet_visibility(&mut self, index: usize, visibility: &str) -> SheetResult<()> {
         if index >= self.worksheets.len() {
-            return Err("Worksheet index out of bounds".into());
+            return Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
+                index
+            ))));
         }
 
         if !matches!(visibility, "visible" | "hidden" | "veryHidden") {
@@ -1573,7 +1804,10 @@ impl Workbook {
     /// ```
     pub fn set_active_sheet(&mut self, index: usize) -> SheetResult<()> {
         if index >= self.worksheets.len() {
-            return Err("Worksheet index out of bounds".into());
+            return Err(Box::new(crate::common::Error::InvalidReference(format!(
+                "Worksheet index {} out of range",
+                index
+            ))));
         }
 
         self.active_sheet_index = index;
@@ -1745,6 +1979,141 @@ impl Workbook {
         crate::ooxml::xlsx::pivot::read_pivot_tables(self.package())
     }
 
+    /// Read all pivot tables with field and item indexes resolved against their caches.
+    ///
+    /// Unlike [`pivot_tables`](Self::pivot_tables), the returned views carry the
+    /// field items and cache records, and can compute the pivot table as a grid
+    /// with [`PivotTableView::to_grid`].
+    pub fn pivot_table_views(&self) -> SheetResult<Vec<PivotTableView>> {
+        crate::ooxml::xlsx::pivot::read_pivot_table_views(self.package())
+    }
+
+    /// Read all charts drawn on the workbook's worksheets.
+    ///
+    /// Charts are located through each worksheet's drawing relationships and
+    /// returned in sheet order. Series names, categories and values come from
+    /// the caches stored in the chart part; references without a cache are
+    /// resolved against the worksheet cells instead.
+    pub fn charts(&self) -> SheetResult<Vec<Chart>> {
+        use crate::ooxml::opc::constants::relationship_type as rt;
+
+        let workbook_uri = PackURI::new("/xl/workbook.xml")?;
+        let workbook_part = self.package().get_part(&workbook_uri)?;
+        let workbook_rels = workbook_part.rels();
+
+        let mut charts = Vec::new();
+        for (index, ws_info) in self.worksheets.iter().enumerate() {
+            let Some(rel) = workbook_rels.get(ws_info.relationship_id.as_str()) else {
+                continue;
+            };
+            let sheet_part = self.package().get_part(&rel.target_partname()?)?;
+
+            for drawing_rel in sheet_part.rels().iter() {
+                if drawing_rel.reltype() != rt::DRAWING || drawing_rel.is_external() {
+                    continue;
+                }
+                let drawing_part = self.package().get_part(&drawing_rel.target_partname()?)?;
+                for chart in read_related_charts(self.package(), drawing_part)? {
+                    charts.push((index, chart));
+                }
+            }
+        }
+
+        let mut loaded: HashMap<usize, Worksheet<'_>> = HashMap::new();
+        let mut resolved = Vec::with_capacity(charts.len());
+        for (index, mut chart) in charts {
+            for series in chart.plot_area.series_mut() {
+                self.fill_series_from_cells(series, index, &mut loaded)?;
+            }
+            resolved.push(chart);
+        }
+
+        Ok(resolved)
+    }
+
+    /// Fill in series data whose reference has no cached values.
+    fn fill_series_from_cells<'s>(
+        &'s self,
+        series: &mut Series,
+        sheet_index: usize,
+        loaded: &mut HashMap<usize, Worksheet<'s>>,
+    ) -> SheetResult<()> {
+        if let Some(TitleText::Reference(source)) = &series.title
+            && let Some(cells) = self.chart_range_values(&source.formula, sheet_index, loaded)?
+        {
+            let text = cells.first().map(chart_cell_text).unwrap_or_default();
+            series.title = Some(TitleText::from_string(text));
+        }
+
+        if let Some(categories) = series.categories.as_mut()
+            && categories.values.is_empty()
+            && let Some(source) = &categories.source_ref
+            && let Some(cells) = self.chart_range_values(&source.formula, sheet_index, loaded)?
+        {
+            categories.values = cells.iter().map(chart_cell_text).collect();
+        }
+
+        for data in [
+            series.values.as_mut(),
+            series.x_values.as_mut(),
+            series.y_values.as_mut(),
+            series.bubble_sizes.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if data.values.is_empty()
+                && let Some(source) = &data.source_ref
+                && let Some(cells) =
+                    self.chart_range_values(&source.formula, sheet_index, loaded)?
+            {
+                data.values = cells.iter().map(chart_cell_number).collect();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read the cells of a chart range reference such as `Sheet1!$B$2:$B$5`.
+    ///
+    /// Unqualified references are resolved against the sheet holding the chart.
+    /// Returns `None` for references that are not a plain cell range.
+    fn chart_range_values<'s>(
+        &'s self,
+        formula: &str,
+        sheet_index: usize,
+        loaded: &mut HashMap<usize, Worksheet<'s>>,
+    ) -> SheetResult<Option<Vec<CellValue>>> {
+        let Some((sheet_name, (first_row, first_col), (last_row, last_col))) =
+            parse_chart_range(formula)
+        else {
+            return Ok(None);
+        };
+
+        let index = match sheet_name {
+            Some(name) => match self.worksheets.iter().position(|ws| ws.name == name) {
+                Some(index) => index,
+                None => return Ok(None),
+            },
+            None => sheet_index,
+        };
+
+        let worksheet = match loaded.entry(index) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(self.get_worksheet(index)?)
+            },
+        };
+
+        let mut values = Vec::new();
+        for row in first_row..=last_row {
+            for col in first_col..=last_col {
+                values.push(worksheet.cell_value(row, col)?.into_owned());
+            }
+        }
+        Ok(Some(values))
+    }
+
     pub fn pivot_tables_on_sheet(&self, sheet_name: &str) -> SheetResult<Vec<PivotTable>> {
         let all = self.pivot_tables()?;
         Ok(all
@@ -1778,7 +2147,8 @@ impl Workbook {
     // - Comments (reading): get_cell_comment(), get_comments()
     // - Data validation (reading): get_data_validations()
     // - Conditional formatting (reading): get_conditional_formatting()
-    // - Page setup (reading): get_page_setup()
+    // - Page setup (reading): get_page_setup(), get_page_margins(), get_print_options()
+    // - Sheet views (reading): sheet_view(), get_freeze_panes()
     //
     // ✅ FULLY IMPLEMENTED (Worksheet writing - via MutableWorksheet):
     // - Cell values & formulas: set_cell_value(), set_cell_formula(), set_cell_formula_with_cache()
@@ -1788,8 +2158,9 @@ impl Workbook {
     // - Hide columns/rows: hide_column(), hide_row(), show_column(), show_row()
     // - Data validation: add_data_validation()
     // - Charts: add_chart() (basic support)
-    // - Freeze panes: freeze_panes(), unfreeze_panes()
-    // - Page setup: set_page_setup(), set_page_setup_with_options(), set_print_area(), clear_print_area()
+    // - Freeze panes: freeze_panes(), split_panes(), unfreeze_panes()
+    // - Sheet views: set_sheet_view(), set_zoom(), set_show_grid_lines(), set_right_to_left(), set_selection()
+    // - Page setup: set_page_setup(), set_page_setup_with_options(), set_page_margins(), set_print_options(), set_print_area(), clear_print_area()
     // - Auto-filter: set_auto_filter(), remove_auto_filter()
     // - Sheet protection: protect_sheet(), protect_sheet_with_options(), unprotect_sheet()
     // - Hyperlinks: set_hyperlink(), remove_hyperlink(), hyperlinks()
@@ -1821,3 +2192,166 @@ impl Workbook {
     // - Advanced features like pivot tables, images would require substantial XML generation code
     // - The library is production-ready for standard Excel CRUD operations
 }
+
+/// A worksheet range as `(sheet, (first_row, first_col), (last_row, last_col))`, 1-based.
+type ChartRange = (Option<String>, (u32, u32), (u32, u32));
+
+/// Split a chart data reference like `'My Sheet'!$A$2:$A$5` into its parts.
+fn parse_chart_range(formula: &str) -> Option<ChartRange> {
+    let formula = formula.trim();
+    let (sheet, cells) = match formula.rsplit_once('!') {
+        Some((sheet, cells)) => {
+            let sheet = match sheet.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+                Some(quoted) => quoted.replace("''", "'"),
+                None => sheet.to_string(),
+            };
+            (Some(sheet), cells)
+        },
+        None => (None, formula),
+    };
+
+    let cells = cells.replace('$', "");
+    let (first, last) = cells.split_once(':').unwrap_or((&cells, &cells));
+    let (first_col, first_row) = Cell::reference_to_coords(first).ok()?;
+    let (last_col, last_row) = Cell::reference_to_coords(last).ok()?;
+    if first_col == 0 || last_col == 0 || first_row == 0 || last_row == 0 {
+        return None;
+    }
+
+    Some((
+        sheet,
+        (first_row.min(last_row), first_col.min(last_col)),
+        (first_row.max(last_row), first_col.max(last_col)),
+    ))
+}
+
+/// Display text of a cell used as a chart label.
+fn chart_cell_text(value: &CellValue) -> String {
+    match value {
+        CellValue::Empty => String::new(),
+        CellValue::Bool(b) => if *b { "TRUE" } else { "FALSE" }.to_string(),
+        CellValue::Int(i) => i.to_string(),
+        CellValue::Float(f) | CellValue::DateTime(f) => f.to_string(),
+        CellValue::String(s) | CellValue::Error(s) => s.clone(),
+        CellValue::Formula { cached_value, .. } => cached_value
+            .as_deref()
+            .map(chart_cell_text)
+            .unwrap_or_default(),
+    }
+}
+
+/// Numeric value of a cell used as a chart data point; non-numeric cells are `NaN`.
+fn chart_cell_number(value: &CellValue) -> f64 {
+    match value {
+        CellValue::Int(i) => *i as f64,
+        CellValue::Float(f) | CellValue::DateTime(f) => *f,
+        CellValue::Bool(b) => f64::from(u8::from(*b)),
+        CellValue::String(s) => s.trim().parse().unwrap_or(f64::NAN),
+        CellValue::Formula { cached_value, .. } => {
+            cached_value.as_deref().map_or(f64::NAN, chart_cell_number)
+        },
+        CellValue::Empty | CellValue::Error(_) => f64::NAN,
+    }
+}
+
+/// Bind the data references of a chart being written to the workbook cells.
+///
+/// References without a sheet name are qualified with the sheet holding the
+/// chart, as Excel requires, and series data without a cache gets one from the
+/// cells so the chart renders before the workbook is recalculated.
+fn bind_chart_to_cells(chart: &mut Chart, sheet_index: usize, worksheets: &[MutableWorksheet]) {
+    let sheet_name = worksheets[sheet_index].name();
+
+    if let Some(TitleText::Reference(source)) = chart.title.as_mut() {
+        source.formula = qualify_chart_range(&source.formula, sheet_name);
+    }
+
+    for series in chart.plot_area.series_mut() {
+        if let Some(TitleText::Reference(source)) = series.title.as_mut() {
+            source.formula = qualify_chart_range(&source.formula, sheet_name);
+        }
+
+        if let Some(categories) = series.categories.as_mut()
+            && let Some(source) = categories.source_ref.as_mut()
+        {
+            source.formula = qualify_chart_range(&source.formula, sheet_name);
+            if categories.values.is_empty()
+                && let Some(cells) = chart_range_cells(&source.formula, worksheets)
+            {
+                categories.values = cells.iter().map(chart_cell_text).collect();
+            }
+        }
+
+        for data in [
+            series.values.as_mut(),
+            series.x_values.as_mut(),
+            series.y_values.as_mut(),
+            series.bubble_sizes.as_mut(),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if let Some(source) = data.source_ref.as_mut() {
+                source.formula = qualify_chart_range(&source.formula, sheet_name);
+                if data.values.is_empty()
+                    && let Some(cells) = chart_range_cells(&source.formula, worksheets)
+                {
+                    data.values = cells.iter().map(chart_cell_number).collect();
+                }
+            }
+        }
+    }
+}
+
+/// Prefix a chart reference like `$B$2:$B$5` with the given sheet name.
+fn qualify_chart_range(formula: &str, sheet_name: &str) -> String {
+    let formula = formula.trim();
+    if formula.contains('!') || parse_chart_range(formula).is_none() {
+        return formula.to_string();
+    }
+
+    // Names that are not plain identifiers, or read like a cell, need quotes
+    let letters = sheet_name
+        .chars()
+        .take_while(char::is_ascii_alphabetic)
+        .count();
+    let looks_like_cell = (1..=3).contains(&letters)
+        && sheet_name.len() > letters
+        && sheet_name[letters..].chars().all(|c| c.is_ascii_digit());
+    let plain = sheet_name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && sheet_name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+        && !looks_like_cell;
+    if plain {
+        format!("{}!{}", sheet_name, formula)
+    } else {
+        format!("'{}'!{}", sheet_name.replace('\'', "''"), formula)
+    }
+}
+
+/// Read the cells of a sheet-qualified chart reference from the worksheets being written.
+fn chart_range_cells(formula: &str, worksheets: &[MutableWorksheet]) -> Option<Vec<CellValue>> {
+    let (Some(sheet_name), (first_row, first_col), (last_row, last_col)) =
+        parse_chart_range(formula)?
+    else {
+        return None;
+    };
+    let worksheet = worksheets.iter().find(|ws| ws.name() == sheet_name)?;
+
+    let mut values = Vec::new();
+    for row in first_row..=last_row {
+        for col in first_col..=last_col {
+            values.push(
+                worksheet
+                    .cell_value(row, col)
+                    .cloned()
+                    .unwrap_or(CellValue::Empty),
+            );
+        }
+    }
+    Some(values)
+}
@@ -5,8 +5,12 @@
 
 use std::borrow::Cow;
 use std::collections::HashMap;
+use std::io::Read;
 
+use crate::common::RGBColor;
+use crate::common::trace;
 use crate::common::xml::unescape_xml;
+use crate::ooxml::drawings::color::ColorSpec;
 use crate::ooxml::opc::PackURI;
 use crate::sheet::{
     Cell as CellTrait, CellIterator, CellValue, Result, RowIterator, Worksheet as WorksheetTrait,
@@ -14,10 +18,19 @@ use crate::sheet::{
 
 use super::RichTextRun;
 use super::cell::{Cell, CellIterator as XlsxCellIterator, RowIterator as XlsxRowIterator};
+use super::comments::{
+    WorksheetComment, build_comment_threads, merge_comments, parse_comments_xml,
+};
+use super::conditional_format::{ConditionalFormatType, parse_conditional_formats};
+use super::data_validation::{DataValidationRule, parse_data_validations};
 use super::format::{CellBorder, CellFill, CellFont, CellFormat};
+use super::page_layout::{PageMargins, PrintOptions};
+use super::references::SharedFormulas;
+use super::rows::RowsIter;
 use super::sort::{SortBy, SortCondition, SortMethod, SortState};
 use super::sparkline::{SparklineGroup, parse_sparkline_groups_from_worksheet_xml};
-use super::views::{SheetView, SheetViewType};
+use super::table::Table;
+use super::views::{Pane, PaneState, PaneType, Selection, SheetView, SheetViewType};
 
 /// Information about a worksheet
 #[derive(Debug, Clone)]
@@ -94,17 +107,6 @@ pub struct Comment {
     pub text: String,
 }
 
-/// Data validation rule information (parsed from worksheet XML)
-#[derive(Debug, Clone)]
-pub struct DataValidationRule {
-    /// Range (e.g., "A1:B10")
-    pub range: String,
-    /// Validation type (e.g., "list", "whole", "decimal")
-    pub validation_type: String,
-    /// Allowed values or formula
-    pub formula: Option<String>,
-}
-
 /// Conditional formatting rule
 #[derive(Debug, Clone)]
 pub struct ConditionalFormatRule {
@@ -114,6 +116,12 @@ pub struct ConditionalFormatRule {
     pub rule_type: String,
     /// Priority
     pub priority: u32,
+    /// Index of the differential format in styles.xml
+    pub dxf_id: Option<u32>,
+    /// Formulas of the rule, in document order
+    pub formulas: Vec<String>,
+    /// Typed rule, when the rule type is supported
+    pub rule: Option<ConditionalFormatType>,
 }
 
 /// Page setup information
@@ -129,6 +137,8 @@ pub struct PageSetup {
     pub fit_to_width: Option<u32>,
     /// Fit to page height
     pub fit_to_height: Option<u32>,
+    /// Whether `fit_to_width`/`fit_to_height` are in effect instead of `scale`
+    pub fit_to_page: bool,
 }
 
 /// Auto-filter information
@@ -156,8 +166,10 @@ pub struct Worksheet<'a> {
     merged_regions: Vec<(u32, u32, u32, u32)>,
     /// Hyperlinks by cell reference
     hyperlinks: HashMap<String, Hyperlink>,
-    /// Comments by cell reference
+    /// Legacy notes by cell reference
     comments: HashMap<String, Comment>,
+    /// Notes and threaded comments, sorted by cell position
+    all_comments: Vec<WorksheetComment>,
     /// Column information by column number
     columns: HashMap<u32, ColumnInfo>,
     /// Row information by row number
@@ -168,6 +180,10 @@ pub struct Worksheet<'a> {
     conditional_formats: Vec<ConditionalFormatRule>,
     /// Page setup
     page_setup: PageSetup,
+    /// Page margins
+    page_margins: Option<PageMargins>,
+    /// Print options
+    print_options: Option<PrintOptions>,
     /// Auto-filter
     auto_filter: Option<AutoFilter>,
     /// Sheet view settings
@@ -178,6 +194,8 @@ pub struct Worksheet<'a> {
     col_breaks: Vec<PageBreak>,
     rich_text_cells: HashMap<(u32, u32), Vec<RichTextRun>>,
     sparkline_groups: Vec<SparklineGroup>,
+    /// Tables (ListObjects) defined on the worksheet
+    tables: Vec<Table>,
 }
 
 impl<'a> Worksheet<'a> {
@@ -192,32 +210,155 @@ impl<'a> Worksheet<'a> {
             merged_regions: Vec::new(),
             hyperlinks: HashMap::new(),
             comments: HashMap::new(),
+            all_comments: Vec::new(),
             columns: HashMap::new(),
             rows: HashMap::new(),
             data_validations: Vec::new(),
             conditional_formats: Vec::new(),
             page_setup: PageSetup::default(),
+            page_margins: None,
+            print_options: None,
             auto_filter: None,
             sheet_view: None,
             row_breaks: Vec::new(),
             col_breaks: Vec::new(),
             rich_text_cells: HashMap::new(),
             sparkline_groups: Vec::new(),
+            tables: Vec::new(),
         }
     }
 
     /// Load worksheet data from the XML.
     pub fn load_data(&mut self) -> Result<()> {
-        // Get the worksheet part using the relationship ID
-        let worksheet_uri =
-            PackURI::new(format!("/xl/worksheets/sheet{}.xml", self.info.sheet_id))?;
-
-        let worksheet_part = self.workbook.package().get_part(&worksheet_uri)?;
-        let content = std::str::from_utf8(worksheet_part.blob())?;
+        let worksheet_uri = self.part_uri()?;
+        let content = self.part_content(&worksheet_uri)?;
+        let content = content.as_ref();
+        let _span = trace::info_span!(
+            "parse_worksheet",
+            name = %self.info.name,
+            part = %worksheet_uri,
+            size = content.len()
+        )
+        .entered();
 
         // Parse worksheet data
         self.parse_worksheet_xml(content)?;
 
+        self.load_comments(&worksheet_uri)?;
+        self.load_tables(&worksheet_uri)?;
+
+        Ok(())
+    }
+
+    /// Stream the worksheet's rows without building the cell map.
+    ///
+    /// Each row is decoded only when the iterator reaches it, and shared
+    /// strings are borrowed from the workbook instead of cloned, which keeps
+    /// memory flat for sheets with millions of rows. This reads the
+    /// worksheet part directly, so it works whether or not
+    /// [`load_data`](Self::load_data) has been called.
+    ///
+    /// The iterator only supports a single top-to-bottom pass. Random access
+    /// (`cell`, `range`, styles, merged regions, ...) still requires the
+    /// eager path.
+    pub fn rows_iter(&self) -> Result<RowsIter<'a>> {
+        let workbook: &'a Workbook = self.workbook;
+        let worksheet_part = workbook.package().get_part(&self.part_uri()?)?;
+        let rows = if worksheet_part.is_deferred() {
+            RowsIter::from_reader(worksheet_part.content_reader()?, workbook.shared_strings())
+        } else {
+            let content = std::str::from_utf8(worksheet_part.try_blob()?)?;
+            RowsIter::new(content, workbook.shared_strings())
+        };
+        Ok(rows.with_date_styles(workbook.styles(), workbook.date_system()))
+    }
+
+    /// URI of the worksheet part.
+    fn part_uri(&self) -> Result<PackURI> {
+        Ok(PackURI::new(format!(
+            "/xl/worksheets/sheet{}.xml",
+            self.info.sheet_id
+        ))?)
+    }
+
+    /// XML content of the worksheet part.
+    ///
+    /// A part that is still in the package file is read into a buffer that
+    /// the caller drops after parsing, so the package does not keep it.
+    fn part_content(&self, worksheet_uri: &PackURI) -> Result<Cow<'a, str>> {
+        let workbook: &'a Workbook = self.workbook;
+        let worksheet_part = workbook.package().get_part(worksheet_uri)?;
+        if worksheet_part.is_deferred() {
+            let mut content = String::new();
+            worksheet_part
+                .content_reader()?
+                .read_to_string(&mut content)?;
+            return Ok(Cow::Owned(content));
+        }
+        Ok(Cow::Borrowed(std::str::from_utf8(
+            worksheet_part.try_blob()?,
+        )?))
+    }
+
+    /// Load legacy notes and threaded comments related to the worksheet.
+    fn load_comments(&mut self, worksheet_uri: &PackURI) -> Result<()> {
+        use crate::ooxml::opc::constants::relationship_type as rt;
+
+        let package = self.workbook.package();
+        let worksheet_part = package.get_part(worksheet_uri)?;
+
+        let mut notes = Vec::new();
+        for rel in worksheet_part.rels().iter() {
+            if rel.reltype() != rt::COMMENTS || rel.is_external() {
+                continue;
+            }
+
+            // Skip a relationship whose part is missing from the package
+            let comments_uri = rel.target_partname()?;
+            let Ok(comments_part) = package.get_part(&comments_uri) else {
+                continue;
+            };
+            let xml = std::str::from_utf8(comments_part.try_blob()?)?;
+            notes.extend(parse_comments_xml(xml)?);
+        }
+
+        let threads = match super::read_threaded_comments(package, worksheet_uri)? {
+            Some(threaded) => {
+                let persons = super::read_persons(package)?;
+                build_comment_threads(&threaded, persons.as_ref())
+            },
+            None => Vec::new(),
+        };
+
+        self.comments = notes
+            .iter()
+            .map(|note| (note.cell_ref.clone(), note.clone()))
+            .collect();
+        self.all_comments = merge_comments(notes, threads);
+
+        Ok(())
+    }
+
+    /// Load the table parts related to the worksheet.
+    fn load_tables(&mut self, worksheet_uri: &PackURI) -> Result<()> {
+        use crate::ooxml::opc::constants::relationship_type as rt;
+
+        let package = self.workbook.package();
+        let worksheet_part = package.get_part(worksheet_uri)?;
+
+        for rel in worksheet_part.rels().iter() {
+            if rel.reltype() != rt::TABLE || rel.is_external() {
+                continue;
+            }
+
+            let table_uri = rel.target_partname()?;
+            let table_part = package.get_part(&table_uri)?;
+            let xml = std::str::from_utf8(table_part.try_blob()?)?;
+            if let Some(table) = Table::parse(xml) {
+                self.tables.push(table);
+            }
+        }
+
         Ok(())
     }
 
@@ -255,20 +396,23 @@ impl<'a> Worksheet<'a> {
             self.parse_columns(cols_content)?;
         }
 
-        // Parse data validations
-        if let Some(dv_start) = content.find("<dataValidations")
-            && let Some(dv_end) = content[dv_start..].find("</dataValidations>")
-        {
-            let dv_content = &content[dv_start..dv_start + dv_end + 18];
-            self.parse_data_validations(dv_content)?;
+        // Parse data validations, including the x14 ones in extLst
+        for (start_tag, end_tag) in [
+            ("<dataValidations", "</dataValidations>"),
+            ("<x14:dataValidations", "</x14:dataValidations>"),
+        ] {
+            if let Some(dv_start) = content.find(start_tag)
+                && let Some(dv_end) = content[dv_start..].find(end_tag)
+            {
+                let dv_content = &content[dv_start..dv_start + dv_end + end_tag.len()];
+                self.data_validations
+                    .extend(parse_data_validations(dv_content)?);
+            }
         }
 
         // Parse conditional formatting
-        if let Some(cf_start) = content.find("<conditionalFormatting")
-            && let Some(cf_end) = content[cf_start..].find("</conditionalFormatting>")
-        {
-            let cf_content = &content[cf_start..cf_start + cf_end + 24];
-            self.parse_conditional_formatting(cf_content)?;
+        if content.contains("<conditionalFormatting") {
+            self.conditional_formats = parse_conditional_formats(content)?;
         }
 
         // Parse page setup
@@ -278,6 +422,25 @@ impl<'a> Worksheet<'a> {
             let ps_content = &content[ps_start..ps_start + ps_end + 2];
             self.parse_page_setup(ps_content)?;
         }
+        if let Some(pr_start) = content.find("<pageSetUpPr ")
+            && let Some(pr_end) = content[pr_start..].find("/>")
+        {
+            let pr_tag = &content[pr_start..pr_start + pr_end];
+            self.page_setup.fit_to_page = Self::extract_attribute(pr_tag, "fitToPage")
+                .is_some_and(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+        }
+
+        // Parse page margins and print options
+        if let Some(pm_start) = content.find("<pageMargins ")
+            && let Some(pm_end) = content[pm_start..].find("/>")
+        {
+            self.page_margins = PageMargins::parse(&content[pm_start..pm_start + pm_end]);
+        }
+        if let Some(po_start) = content.find("<printOptions")
+            && let Some(po_end) = content[po_start..].find("/>")
+        {
+            self.print_options = PrintOptions::parse(&content[po_start..po_start + po_end]);
+        }
 
         // Parse auto-filter
         if let Some(af_start) = content.find("<autoFilter ") {
@@ -328,13 +491,18 @@ impl<'a> Worksheet<'a> {
         let mut max_row = 0;
         let mut min_col = u32::MAX;
         let mut max_col = 0;
+        let mut shared_formulas = SharedFormulas::default();
+        let styles = self.workbook.styles();
+        let date_system = self.workbook.date_system();
 
         while let Some(row_start) = sheet_data[pos..].find("<row ") {
             let row_start_pos = pos + row_start;
             if let Some(row_end) = sheet_data[row_start_pos..].find("</row>") {
                 let row_content = &sheet_data[row_start_pos..row_start_pos + row_end + 6];
 
-                if let Some((row_num, row_info, cells)) = self.parse_row_xml(row_content)? {
+                if let Some((row_num, row_info, cells)) =
+                    self.parse_row_xml(row_content, &mut shared_formulas)?
+                {
                     min_row = min_row.min(row_num);
                     max_row = max_row.max(row_num);
 
@@ -347,6 +515,13 @@ impl<'a> Worksheet<'a> {
                         min_col = min_col.min(col_num);
                         max_col = max_col.max(col_num);
 
+                        let value = match style_idx {
+                            Some(idx) if styles.is_date_style(idx as usize) => {
+                                date_system.date_cell_value(value)
+                            },
+                            _ => value,
+                        };
+
                         self.cells
                             .entry(row_num)
                             .or_default()
@@ -383,6 +558,7 @@ impl<'a> Worksheet<'a> {
     fn parse_row_xml(
         &self,
         row_content: &str,
+        shared_formulas: &mut SharedFormulas,
     ) -> Result<
         Option<(
             u32,
@@ -440,7 +616,7 @@ impl<'a> Worksheet<'a> {
                 let c_content = &row_content[c_start_pos..c_start_pos + c_end + 4];
 
                 if let Some((col_num, value, style_idx, rich_runs)) =
-                    self.parse_cell_xml(c_content)?
+                    self.parse_cell_xml(row_num, c_content, shared_formulas)?
                 {
                     cells.push((col_num, value, style_idx, rich_runs));
                 }
@@ -455,10 +631,15 @@ impl<'a> Worksheet<'a> {
     }
 
     /// Parse a single cell XML.
+    ///
+    /// Cells of a shared formula group get the group's formula translated to
+    /// their position; the masters seen so far are kept in `shared_formulas`.
     #[allow(clippy::type_complexity)] // TODO: Refactor the return type
     fn parse_cell_xml(
         &self,
+        row_num: u32,
         cell_content: &str,
+        shared_formulas: &mut SharedFormulas,
     ) -> Result<Option<(u32, CellValue, Option<u32>, Option<Vec<RichTextRun>>)>> {
         // Extract cell reference (e.g., "A1")
         let reference = if let Some(r_start) = cell_content.find("r=\"") {
@@ -476,7 +657,7 @@ impl<'a> Worksheet<'a> {
         };
 
         // Convert reference to row/col numbers
-        let (col_num, _row_num) = Cell::reference_to_coords(&reference)?;
+        let (col_num, _) = Cell::reference_to_coords(&reference)?;
 
         // Extract style index (s attribute)
         let style_idx = if let Some(s_start) = cell_content.find(" s=\"") {
@@ -519,21 +700,37 @@ impl<'a> Worksheet<'a> {
             if let Some(gt_rel) = f_content.find('>') {
                 let tag_end = f_start + gt_rel + 1;
                 let f_tag = &cell_content[f_start..tag_end];
+                let formula_type = Self::extract_attribute(f_tag, "t");
 
-                // Detect array formulas: <f t="array" ref="A1:C3">...
-                if f_tag.contains("t=\"array\"") {
+                // Array formulas cover a range: <f t="array" ref="A1:C3">...
+                if formula_type.as_deref() == Some("array") {
                     is_array_formula = true;
-                }
-                if let Some(r) = Self::extract_attribute(f_tag, "ref") {
-                    array_ref = Some(r);
+                    array_ref = Self::extract_attribute(f_tag, "ref");
                 }
 
                 let text_start = tag_end;
-                if let Some(end_rel) = cell_content[text_start..].find("</f>") {
-                    let raw = &cell_content[text_start..text_start + end_rel];
-                    Some(unescape_xml(raw))
-                } else {
+                let text = if f_tag.ends_with("/>") {
                     None
+                } else {
+                    cell_content[text_start..].find("</f>").map(|end_rel| {
+                        unescape_xml(&cell_content[text_start..text_start + end_rel])
+                    })
+                };
+
+                // Only the master cell of a shared formula group has text:
+                // <f t="shared" ref="B2:B9" si="0">A2*2</f>, then <f t="shared" si="0"/>
+                match (
+                    formula_type.as_deref(),
+                    Self::extract_attribute(f_tag, "si"),
+                ) {
+                    (Some("shared"), Some(si)) => match text.filter(|text| !text.is_empty()) {
+                        Some(text) => {
+                            shared_formulas.insert(&si, row_num, col_num, &text);
+                            Some(text)
+                        },
+                        None => shared_formulas.get(&si, row_num, col_num),
+                    },
+                    _ => text,
                 }
             } else {
                 None
@@ -599,7 +796,7 @@ impl<'a> Worksheet<'a> {
     }
 
     /// Extract concatenated text from an inline string cell (<is> ... </is>).
-    fn extract_inline_string_text(cell_content: &str) -> Option<String> {
+    pub(super) fn extract_inline_string_text(cell_content: &str) -> Option<String> {
         let bytes = cell_content.as_bytes();
         let mut result = String::new();
         let mut search_start = 0;
@@ -899,74 +1096,6 @@ impl<'a> Worksheet<'a> {
         Ok(())
     }
 
-    /// Parse data validations from XML.
-    fn parse_data_validations(&mut self, content: &str) -> Result<()> {
-        let mut pos = 0;
-        while let Some(dv_start) = content[pos..].find("<dataValidation ") {
-            let dv_start_pos = pos + dv_start;
-            if let Some(dv_end) = content[dv_start_pos..].find("</dataValidation>") {
-                let dv_tag = &content[dv_start_pos..dv_start_pos + dv_end + 17];
-
-                let range = Self::extract_attribute(dv_tag, "sqref");
-                let validation_type = Self::extract_attribute(dv_tag, "type");
-                let formula = if let Some(formula_start) = dv_tag.find("<formula1>")
-                    && let Some(formula_end) = dv_tag[formula_start..].find("</formula1>")
-                {
-                    Some(dv_tag[formula_start + 10..formula_start + formula_end].to_string())
-                } else {
-                    None
-                };
-
-                if let Some(range_val) = range {
-                    self.data_validations.push(DataValidationRule {
-                        range: range_val,
-                        validation_type: validation_type.unwrap_or_else(|| String::from("list")),
-                        formula,
-                    });
-                }
-
-                pos = dv_start_pos + dv_end + 17;
-            } else {
-                break;
-            }
-        }
-        Ok(())
-    }
-
-    /// Parse conditional formatting from XML.
-    fn parse_conditional_formatting(&mut self, content: &str) -> Result<()> {
-        // Extract sqref attribute for the range
-        let range = Self::extract_attribute(content, "sqref");
-
-        if let Some(range_val) = range {
-            let mut pos = 0;
-            while let Some(rule_start) = content[pos..].find("<cfRule ") {
-                let rule_start_pos = pos + rule_start;
-                if let Some(rule_end) = content[rule_start_pos..].find("/>") {
-                    let rule_tag = &content[rule_start_pos..rule_start_pos + rule_end + 2];
-
-                    let rule_type = Self::extract_attribute(rule_tag, "type");
-                    let priority = Self::extract_attribute(rule_tag, "priority")
-                        .and_then(|s| s.parse::<u32>().ok());
-
-                    if let (Some(type_val), Some(priority_val)) = (rule_type, priority) {
-                        self.conditional_formats.push(ConditionalFormatRule {
-                            range: range_val.clone(),
-                            rule_type: type_val,
-                            priority: priority_val,
-                        });
-                    }
-
-                    pos = rule_start_pos + rule_end + 2;
-                } else {
-                    break;
-                }
-            }
-        }
-
-        Ok(())
-    }
-
     /// Parse page setup from XML.
     fn parse_page_setup(&mut self, content: &str) -> Result<()> {
         let paper_size =
@@ -984,6 +1113,7 @@ impl<'a> Worksheet<'a> {
             scale,
             fit_to_width,
             fit_to_height,
+            fit_to_page: self.page_setup.fit_to_page,
         };
 
         Ok(())
@@ -1013,7 +1143,7 @@ impl<'a> Worksheet<'a> {
     }
 
     /// Parse sort state from XML.
-    fn parse_sort_state(content: &str, fallback_ref: Option<&str>) -> Option<SortState> {
+    pub(crate) fn parse_sort_state(content: &str, fallback_ref: Option<&str>) -> Option<SortState> {
         let tag_end = content.find('>').unwrap_or(content.len());
         let tag = &content[..tag_end];
         let ref_range =
@@ -1079,11 +1209,67 @@ impl<'a> Worksheet<'a> {
             && let Some(view_end) = content[view_start..].find('>')
         {
             let view_tag = &content[view_start..view_start + view_end + 1];
-            self.sheet_view = Some(Self::parse_sheet_view_tag(view_tag));
+            let mut view = Self::parse_sheet_view_tag(view_tag);
+
+            if !view_tag.ends_with("/>") {
+                let body_start = view_start + view_end + 1;
+                let body_end = content[body_start..]
+                    .find("</sheetView>")
+                    .map_or(content.len(), |end| body_start + end);
+                let body = &content[body_start..body_end];
+
+                if let Some(pane_start) = body.find("<pane ")
+                    && let Some(pane_end) = body[pane_start..].find("/>")
+                {
+                    view.pane = Some(Self::parse_pane_tag(
+                        &body[pane_start..pane_start + pane_end],
+                    ));
+                }
+
+                let mut pos = 0;
+                while let Some(sel_start) = body[pos..].find("<selection") {
+                    let sel_start_pos = pos + sel_start;
+                    let Some(sel_end) = body[sel_start_pos..].find("/>") else {
+                        break;
+                    };
+                    let sel_tag = &body[sel_start_pos..sel_start_pos + sel_end];
+                    view.selections.push(Selection {
+                        pane: Self::extract_attribute(sel_tag, "pane")
+                            .as_deref()
+                            .and_then(PaneType::parse),
+                        active_cell: Self::extract_attribute(sel_tag, "activeCell"),
+                        sqref: Self::extract_attribute(sel_tag, "sqref"),
+                    });
+                    pos = sel_start_pos + sel_end + 2;
+                }
+            }
+
+            self.sheet_view = Some(view);
         }
         Ok(())
     }
 
+    /// Parse the <pane> tag into a Pane struct.
+    fn parse_pane_tag(tag: &str) -> Pane {
+        let split = |name: &str| {
+            Self::extract_attribute(tag, name)
+                .and_then(|v| v.parse::<f64>().ok())
+                .unwrap_or(0.0)
+        };
+
+        Pane {
+            x_split: split("xSplit"),
+            y_split: split("ySplit"),
+            top_left_cell: Self::extract_attribute(tag, "topLeftCell"),
+            active_pane: Self::extract_attribute(tag, "activePane")
+                .as_deref()
+                .and_then(PaneType::parse),
+            state: Self::extract_attribute(tag, "state")
+                .as_deref()
+                .and_then(PaneState::parse),
+        }
+    }
+
     /// Parse the <sheetView> tag into a SheetView struct.
     fn parse_sheet_view_tag(tag: &str) -> SheetView {
         let show_formulas = Self::extract_attribute(tag, "showFormulas")
@@ -1115,6 +1301,8 @@ impl<'a> Worksheet<'a> {
             top_left_cell,
             zoom_scale,
             zoom_scale_normal,
+            pane: None,
+            selections: Vec::new(),
         }
     }
 
@@ -1345,6 +1533,11 @@ impl<'a> Worksheet<'a> {
         self.sheet_view.as_ref()
     }
 
+    /// Get the number of frozen `(rows, columns)`, if panes are frozen.
+    pub fn get_freeze_panes(&self) -> Option<(u32, u32)> {
+        self.sheet_view.as_ref()?.pane.as_ref()?.frozen_rows_cols()
+    }
+
     /// Find cells containing specific text.
     ///
     /// # Arguments
@@ -1559,19 +1752,61 @@ impl<'a> Worksheet<'a> {
         })
     }
 
+    /// Get the fill color of a cell as written in the styles.
+    ///
+    /// This is the foreground color of the cell's pattern fill, which is
+    /// the whole background for solid fills. Returns None for cells without
+    /// a pattern fill.
+    ///
+    /// # Arguments
+    /// * `row` - Row number (1-based)
+    /// * `column` - Column number (1-based)
+    pub fn get_cell_fill_color_spec(&self, row: u32, column: u32) -> Option<ColorSpec> {
+        let fill_id = self.get_cell_style(row, column)?.fill_id?;
+        match self.workbook.styles().get_fill(fill_id as usize)? {
+            super::styles::Fill::Pattern {
+                pattern_type,
+                fg_color,
+                ..
+            } if pattern_type != "none" => ColorSpec::from_spreadsheet(fg_color.as_deref()?),
+            _ => None,
+        }
+    }
+
+    /// Get the fill color of a cell, resolving theme colors against the
+    /// workbook theme.
+    ///
+    /// # Arguments
+    /// * `row` - Row number (1-based)
+    /// * `column` - Column number (1-based)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::xlsx::Workbook;
+    ///
+    /// let workbook = Workbook::open("styled.xlsx")?;
+    /// let sheet = workbook.worksheet(0)?;
+    /// if let Some(color) = sheet.get_cell_fill_color(1, 1) {
+    ///     println!("A1 fill: #{}", color.to_hex());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn get_cell_fill_color(&self, row: u32, column: u32) -> Option<RGBColor> {
+        self.get_cell_fill_color_spec(row, column)?
+            .resolve(self.workbook.theme_colors())
+    }
+
     /// Check if a cell is formatted as a date.
     ///
     /// # Arguments
     /// * `row` - Row number (1-based)
     /// * `column` - Column number (1-based)
     pub fn is_date_formatted(&self, row: u32, column: u32) -> bool {
-        if let Some(style) = self.get_cell_style(row, column)
-            && let Some(num_fmt_id) = style.num_fmt_id
-            && let Some(num_fmt) = self.workbook.styles().get_number_format(num_fmt_id)
-        {
-            return num_fmt.is_date_format();
-        }
-        false
+        self.cell_styles
+            .get(&row)
+            .and_then(|row_styles| row_styles.get(&column))
+            .is_some_and(|style_idx| self.workbook.styles().is_date_style(*style_idx as usize))
     }
 
     /// Get the date/time value from a cell formatted as a date.
@@ -1587,7 +1822,7 @@ impl<'a> Worksheet<'a> {
         }
 
         match self.get_cell_value(row, column) {
-            CellValue::Float(f) => Some(f),
+            CellValue::DateTime(f) | CellValue::Float(f) => Some(f),
             CellValue::Int(i) => Some(i as f64),
             _ => None,
         }
@@ -1700,11 +1935,41 @@ impl<'a> Worksheet<'a> {
         self.comments.get(&cell_ref)
     }
 
-    /// Get all comments in the worksheet.
+    /// Get all legacy notes in the worksheet, keyed by cell reference.
+    ///
+    /// This includes the backwards-compatibility notes Excel writes for
+    /// threaded comments. Use [`Worksheet::comments`] to get threaded
+    /// comments with their replies instead.
     pub fn get_comments(&self) -> &HashMap<String, Comment> {
         &self.comments
     }
 
+    /// Get all notes and threaded comments in the worksheet.
+    ///
+    /// Comments are sorted by row, then column. Threaded comments carry
+    /// their replies and resolved author names; the legacy notes Excel
+    /// writes alongside them for older readers are not repeated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::xlsx::{Workbook, WorksheetComment};
+    ///
+    /// let wb = Workbook::open("workbook.xlsx")?;
+    /// let ws = wb.worksheet(0)?;
+    ///
+    /// for comment in ws.comments() {
+    ///     println!("{}: {}", comment.cell_ref(), comment.text());
+    ///     for reply in comment.replies() {
+    ///         println!("  reply: {}", reply.text);
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn comments(&self) -> &[WorksheetComment] {
+        &self.all_comments
+    }
+
     // ===== Column Operations =====
 
     /// Get the width of a specific column.
@@ -1783,6 +2048,27 @@ impl<'a> Worksheet<'a> {
         &self.data_validations
     }
 
+    // ===== Tables =====
+
+    /// Get the tables (ListObjects) defined on the worksheet.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::xlsx::Workbook;
+    ///
+    /// let wb = Workbook::open("report.xlsx")?;
+    /// let ws = wb.worksheet(0)?;
+    ///
+    /// for table in ws.tables() {
+    ///     println!("{} ({}): {:?}", table.name, table.ref_range, table.column_names());
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn tables(&self) -> &[Table] {
+        &self.tables
+    }
+
     // ===== Conditional Formatting =====
 
     /// Get all conditional formatting rules in the worksheet.
@@ -1805,6 +2091,17 @@ impl<'a> Worksheet<'a> {
         &self.conditional_formats
     }
 
+    /// Get all conditional formatting rules with a supported rule type.
+    ///
+    /// Covers every rule type the writer can produce, so rules written with
+    /// [`MutableWorksheet::add_conditional_formatting`](super::MutableWorksheet::add_conditional_formatting)
+    /// read back as the same [`ConditionalFormatType`].
+    pub fn conditional_formats(&self) -> impl Iterator<Item = (&str, &ConditionalFormatType)> {
+        self.conditional_formats
+            .iter()
+            .filter_map(|rule| rule.rule.as_ref().map(|r| (rule.range.as_str(), r)))
+    }
+
     // ===== Page Setup =====
 
     /// Get the page setup information.
@@ -1828,6 +2125,16 @@ impl<'a> Worksheet<'a> {
         &self.page_setup
     }
 
+    /// Get the page margins, if the worksheet defines them.
+    pub fn get_page_margins(&self) -> Option<&PageMargins> {
+        self.page_margins.as_ref()
+    }
+
+    /// Get the print options, if the worksheet defines them.
+    pub fn get_print_options(&self) -> Option<&PrintOptions> {
+        self.print_options.as_ref()
+    }
+
     // ===== Auto-Filter =====
 
     /// Get the auto-filter information.
@@ -1906,7 +2213,7 @@ impl<'a> Worksheet<'a> {
     // ✅ Cell types (advanced): get_cell_type() via CellValue enum
     // ✅ Date cells: is_date_formatted(), get_date_cell_value()
     // ✅ Cell hyperlinks: get_hyperlink(), get_hyperlinks()
-    // ✅ Cell comments: get_cell_comment(), get_comments()
+    // ✅ Cell comments: get_cell_comment(), get_comments(), comments()
     // ✅ Merged regions: get_merged_regions(), is_merged_cell(), get_merge_region()
     // ✅ Column operations: get_column_width(), is_column_hidden(), get_column_info()
     // ✅ Row operations: is_row_hidden(), get_row_height(), get_row_info()
@@ -2011,6 +2318,10 @@ impl<'a> WorksheetTrait for Worksheet<'a> {
         // XLSX values need shared string resolution, so we return owned
         Ok(Cow::Owned(self.get_cell_value(row, column)))
     }
+
+    fn is_date_formatted(&self, row: u32, column: u32) -> bool {
+        Worksheet::is_date_formatted(self, row, column)
+    }
 }
 
 #[cfg(test)]
@@ -2031,6 +2342,655 @@ mod tests {
         let text = Worksheet::extract_inline_string_text(xml).unwrap();
         assert_eq!(text, "Hello World");
     }
+
+    #[test]
+    fn cell_fill_color_resolves_theme_tint() {
+        use crate::common::RGBColor;
+        use crate::ooxml::drawings::color::{ColorSpec, ThemeColorSlot};
+        use crate::ooxml::xlsx::Workbook;
+
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/test-data/ooxml/xlsx/styles.xlsx"
+        );
+        let workbook = Workbook::open(path).unwrap();
+        let sheet = workbook.worksheet(0).unwrap();
+
+        // A11 is filled with "Text 2, Lighter 40%" (theme 3, tint 0.4)
+        assert!(matches!(
+            sheet.get_cell_fill_color_spec(11, 1),
+            Some(ColorSpec::Theme {
+                slot: ThemeColorSlot::Dark2,
+                ..
+            })
+        ));
+        // Excel displays 548DD4 (1F497D lightened by 40%)
+        let color = sheet.get_cell_fill_color(11, 1).unwrap();
+        let expected = RGBColor::new(0x54, 0x8D, 0xD4);
+        assert!(color.r.abs_diff(expected.r) <= 1);
+        assert!(color.g.abs_diff(expected.g) <= 1);
+        assert!(color.b.abs_diff(expected.b) <= 1);
+        assert_eq!(sheet.get_cell_fill_color(1, 1), None);
+    }
+
+    #[test]
+    fn data_validations_round_trip() {
+        use crate::ooxml::xlsx::{
+            DataValidationOperator, DataValidationRule, DataValidationType, Workbook,
+        };
+
+        let rules = vec![
+            DataValidationRule::new(
+                "A2:A50 C2:C50",
+                DataValidationType::Whole {
+                    operator: DataValidationOperator::Between,
+                    value1: "1".to_string(),
+                    value2: Some("100".to_string()),
+                },
+            )
+            .with_input_message("Quantity", "1 to 100")
+            .with_error_message("Out of range", "Enter a whole number from 1 to 100."),
+            DataValidationRule::new(
+                "B2:B50",
+                DataValidationType::List {
+                    values: vec!["Open".to_string(), "Closed".to_string()],
+                },
+            ),
+            DataValidationRule::new(
+                "D2:D50",
+                DataValidationType::ListRange {
+                    source: "$Z$1:$Z$4".to_string(),
+                },
+            ),
+        ];
+
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        ws.set_cell_value(1, 1, "Quantity");
+        for rule in &rules {
+            ws.add_data_validation(rule.clone());
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("validations.xlsx");
+        wb.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let wb = Workbook::open(&path).unwrap();
+        let sheet = wb.worksheet(0).unwrap();
+        assert_eq!(sheet.get_data_validations(), rules.as_slice());
+    }
+
+    #[test]
+    fn tables_round_trip() {
+        use crate::ooxml::xlsx::Workbook;
+
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        ws.set_cell_value(1, 1, "Region");
+        ws.set_cell_value(2, 1, "North");
+        ws.set_cell_value(2, 2, 1250.0);
+        ws.add_table("A1:B2", "Sales", &[], Some("TableStyleMedium9"))
+            .unwrap();
+        let second = wb.add_worksheet("Other");
+        second
+            .add_table("C3:D5", "People", &["Name", "Age & Rank"], None)
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tables.xlsx");
+        wb.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let wb = Workbook::open(&path).unwrap();
+        let sheet = wb.worksheet(0).unwrap();
+        let tables = sheet.tables();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].id, 1);
+        assert_eq!(tables[0].name, "Sales");
+        assert_eq!(tables[0].ref_range, "A1:B2");
+        assert_eq!(tables[0].column_names(), ["Region", "Column2"]);
+        assert_eq!(tables[0].auto_filter_range.as_deref(), Some("A1:B2"));
+        let style = tables[0].style_info.as_ref().unwrap();
+        assert_eq!(style.name.as_deref(), Some("TableStyleMedium9"));
+        assert_eq!(
+            sheet.row_values(1).unwrap()[1],
+            crate::sheet::CellValue::String("Column2".to_string())
+        );
+
+        let sheet = wb.worksheet(1).unwrap();
+        let tables = sheet.tables();
+        assert_eq!(tables[0].id, 2);
+        assert_eq!(tables[0].column_names(), ["Name", "Age & Rank"]);
+    }
+
+    #[test]
+    fn view_and_print_settings_round_trip() {
+        use crate::ooxml::xlsx::{PageMargins, PaneState, PaneType, PrintOptions, Workbook};
+
+        let margins = PageMargins {
+            left: 0.5,
+            right: 0.5,
+            top: 1.0,
+            bottom: 1.0,
+            header: 0.4,
+            footer: 0.4,
+        };
+        let options = PrintOptions {
+            horizontal_centered: true,
+            grid_lines: true,
+            ..Default::default()
+        };
+
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        ws.set_cell_value(1, 1, "Header");
+        ws.freeze_panes(1, 2);
+        ws.set_selection("C3:D4");
+        ws.set_zoom(85);
+        ws.set_show_grid_lines(false);
+        ws.set_right_to_left(true);
+        ws.set_page_setup_with_options("landscape", 9, None, Some(1), Some(0));
+        ws.set_page_margins(margins);
+        ws.set_print_options(options);
+        ws.set_print_area("A1:D20");
+        ws.set_repeating_rows("$1:$1");
+        let split = wb.add_worksheet("Split");
+        split.split_panes(2400.0, 0.0);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("layout.xlsx");
+        wb.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let wb = Workbook::open(&path).unwrap();
+        let sheet = wb.worksheet(0).unwrap();
+        assert_eq!(sheet.get_freeze_panes(), Some((1, 2)));
+        let view = sheet.sheet_view().unwrap();
+        assert_eq!(view.zoom_scale, Some(85));
+        assert_eq!(view.show_grid_lines, Some(false));
+        assert_eq!(view.right_to_left, Some(true));
+        let pane = view.pane.as_ref().unwrap();
+        assert_eq!(pane.top_left_cell.as_deref(), Some("C2"));
+        assert_eq!(pane.active_pane, Some(PaneType::BottomRight));
+        assert_eq!(view.selections.len(), 1);
+        assert_eq!(view.selections[0].pane, Some(PaneType::BottomRight));
+        assert_eq!(view.selections[0].active_cell.as_deref(), Some("C3"));
+        assert_eq!(view.selections[0].sqref.as_deref(), Some("C3:D4"));
+
+        let setup = sheet.get_page_setup();
+        assert!(setup.landscape);
+        assert_eq!(setup.paper_size, Some(9));
+        assert!(setup.fit_to_page);
+        assert_eq!(setup.fit_to_width, Some(1));
+        assert_eq!(setup.fit_to_height, Some(0));
+        assert_eq!(sheet.get_page_margins(), Some(&margins));
+        assert_eq!(sheet.get_print_options(), Some(&options));
+        assert_eq!(sheet.get_print_area(), Some("A1:D20"));
+        assert_eq!(sheet.get_repeating_rows(), Some("$1:$1"));
+
+        let split = wb.worksheet(1).unwrap();
+        let pane = split.sheet_view().unwrap().pane.as_ref().unwrap();
+        assert_eq!(pane.x_split, 2400.0);
+        assert_eq!(pane.state, None);
+        assert_eq!(split.get_freeze_panes(), None);
+
+        // Settings survive being copied into a rebuilt workbook
+        let mut copy = Workbook::create().unwrap();
+        copy.worksheet_mut(0)
+            .unwrap()
+            .copy_view_and_print_settings(&sheet);
+        let copy_path = dir.path().join("copy.xlsx");
+        copy.save(&copy_path).unwrap();
+
+        let copy = Workbook::open(&copy_path).unwrap();
+        let copied = copy.worksheet(0).unwrap();
+        assert_eq!(copied.sheet_view(), sheet.sheet_view());
+        assert_eq!(copied.get_page_margins(), Some(&margins));
+        assert_eq!(copied.get_print_options(), Some(&options));
+        assert_eq!(copied.get_page_setup().fit_to_height, Some(0));
+        assert_eq!(copied.get_print_area(), Some("A1:D20"));
+        assert_eq!(copied.get_repeating_rows(), Some("$1:$1"));
+        assert_eq!(
+            copied.sheet_view().unwrap().pane.as_ref().unwrap().state,
+            Some(PaneState::Frozen)
+        );
+    }
+
+    #[test]
+    fn duplicate_table_names_fail_to_save() {
+        use crate::ooxml::xlsx::Workbook;
+
+        let mut wb = Workbook::create().unwrap();
+        wb.worksheet_mut(0)
+            .unwrap()
+            .add_table("A1:A2", "Data", &[], None)
+            .unwrap();
+        wb.add_worksheet("Second")
+            .add_table("A1:A2", "DATA", &[], None)
+            .unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        assert!(wb.save(dir.path().join("dupes.xlsx")).is_err());
+    }
+
+    #[test]
+    fn worksheets_par_preserves_sheet_order() {
+        use crate::ooxml::xlsx::Workbook;
+        use crate::sheet::CellValue;
+
+        let mut wb = Workbook::create().unwrap();
+        wb.worksheet_mut(0).unwrap().set_cell_value(1, 1, "sheet 0");
+        for i in 1..6 {
+            let ws = wb.add_worksheet(&format!("Sheet{}", i + 1));
+            ws.set_cell_value(1, 1, format!("sheet {i}"));
+            ws.set_cell_value(2, 1, i as f64);
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("par.xlsx");
+        wb.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let wb = Workbook::open(&path).unwrap();
+        let sheets = wb.worksheets_par().unwrap();
+        assert_eq!(sheets.len(), 6);
+        for (i, sheet) in sheets.iter().enumerate() {
+            assert_eq!(sheet.info().name, format!("Sheet{}", i + 1));
+            assert_eq!(
+                sheet.get_cell_value(1, 1),
+                CellValue::String(format!("sheet {i}"))
+            );
+            let sequential = wb.worksheet(i).unwrap();
+            assert_eq!(
+                sheet.non_empty_cell_count(),
+                sequential.non_empty_cell_count()
+            );
+        }
+    }
+
+    #[test]
+    fn rows_iter_matches_eager_cells() {
+        use crate::ooxml::xlsx::Workbook;
+
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        for row in 1..=50u32 {
+            ws.set_cell_value(row, 1, format!("name {}", row % 7));
+            ws.set_cell_value(row, 2, row as f64 * 0.5);
+            if row % 3 == 0 {
+                ws.set_cell_value(row, 4, row % 2 == 0);
+            }
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rows.xlsx");
+        wb.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let wb = Workbook::open(&path).unwrap();
+        let eager = wb.worksheet(0).unwrap();
+        let mut count = 0;
+        for row in wb.rows_iter(0).unwrap() {
+            let row = row.unwrap();
+            for (col, value) in row.into_cell_values() {
+                assert_eq!(value, eager.get_cell_value(count + 1, col));
+            }
+            count += 1;
+        }
+        assert_eq!(count, 50);
+        assert_eq!(eager.rows_iter().unwrap().skip_rows(45).count(), 5);
+    }
+
+    #[test]
+    fn deferred_parts_are_streamed() {
+        use crate::ooxml::opc::{OpcPackage, PackURI};
+        use crate::ooxml::xlsx::Workbook;
+
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        for row in 1..=50u32 {
+            ws.set_cell_value(row, 1, format!("name {}", row % 7));
+            ws.set_cell_value(row, 2, row as f64 * 0.5);
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let generated = dir.path().join("rows.xlsx");
+        wb.save(&generated).unwrap();
+
+        for path in [
+            generated.as_path(),
+            "test-data/ooxml/xlsx/NewlineInFormulas.xlsx".as_ref(),
+        ] {
+            let loaded = Workbook::open(path).unwrap();
+            let deferred = Workbook::new(OpcPackage::open_deferring(path, 0).unwrap()).unwrap();
+            let shared_strings = PackURI::new("/xl/sharedStrings.xml").unwrap();
+            let sheet = PackURI::new("/xl/worksheets/sheet1.xml").unwrap();
+
+            let rows: Vec<_> = deferred.rows_iter(0).unwrap().map(Result::unwrap).collect();
+            let expected: Vec<_> = loaded.rows_iter(0).unwrap().map(Result::unwrap).collect();
+            assert_eq!(rows, expected);
+            assert_eq!(
+                deferred.rows_iter(0).unwrap().skip_rows(3).count(),
+                expected.len().saturating_sub(3)
+            );
+
+            let eager = deferred.worksheet(0).unwrap();
+            let loaded_eager = loaded.worksheet(0).unwrap();
+            for row in &expected {
+                for (col, _) in row.cells() {
+                    assert_eq!(
+                        eager.get_cell_value(row.index(), *col),
+                        loaded_eager.get_cell_value(row.index(), *col)
+                    );
+                }
+            }
+
+            // Neither part was read into the package
+            let package = deferred.package();
+            if package.contains_part(&shared_strings) {
+                assert!(package.get_part(&shared_strings).unwrap().is_deferred());
+            }
+            assert!(package.get_part(&sheet).unwrap().is_deferred());
+        }
+    }
+
+    #[test]
+    fn date_cells_use_1904_date_system() {
+        use crate::ooxml::xlsx::Workbook;
+        use crate::sheet::{CellValue, DateSystem};
+
+        let wb = Workbook::open("test-data/ooxml/xlsx/DateFormatTests.xlsx").unwrap();
+        assert_eq!(wb.date_system(), DateSystem::Excel1904);
+        let sheet = wb.worksheet(0).unwrap();
+
+        let date = sheet.get_cell_value(2, 3);
+        assert!(matches!(date, CellValue::DateTime(_)), "{date:?}");
+        let datetime = date.as_datetime(true).unwrap();
+        assert_eq!(datetime.to_string(), "1952-10-11 14:35:27");
+        assert!(!matches!(
+            sheet.get_cell_value(2, 2),
+            CellValue::DateTime(_)
+        ));
+
+        let row = wb
+            .rows_iter(0)
+            .unwrap()
+            .skip_rows(1)
+            .next()
+            .unwrap()
+            .unwrap();
+        let streamed = row
+            .into_cell_values()
+            .into_iter()
+            .find(|(col, _)| *col == 3)
+            .unwrap()
+            .1;
+        assert_eq!(streamed, date);
+    }
+
+    #[test]
+    fn shared_and_array_formulas_are_expanded() {
+        use crate::ooxml::xlsx::Workbook;
+        use crate::sheet::CellValue;
+
+        fn formula(value: CellValue) -> Option<(String, bool, Option<String>)> {
+            match value {
+                CellValue::Formula {
+                    formula,
+                    is_array,
+                    array_range,
+                    ..
+                } => Some((formula.replace(['\r', '\n'], ""), is_array, array_range)),
+                _ => None,
+            }
+        }
+
+        // B3:B10 share the master formula `B1+B2` of B3
+        let wb = Workbook::open("test-data/ooxml/xlsx/NewlineInFormulas.xlsx").unwrap();
+        let ws = wb.worksheet(0).unwrap();
+        let shared = |row, col| formula(ws.get_cell_value(row, col));
+        assert_eq!(shared(3, 2), Some(("B1+B2".to_string(), false, None)));
+        assert_eq!(shared(4, 2), Some(("B2+B3".to_string(), false, None)));
+        assert_eq!(shared(10, 2), Some(("B8+B9".to_string(), false, None)));
+
+        let streamed: Vec<_> = wb
+            .rows_iter(0)
+            .unwrap()
+            .skip_rows(5)
+            .flat_map(|row| {
+                let row = row.unwrap();
+                let index = row.index();
+                row.into_cell_values()
+                    .into_iter()
+                    .map(move |(col, value)| (index, col, value))
+            })
+            .collect();
+        assert!(
+            streamed
+                .iter()
+                .any(|(_, _, value)| formula(value.clone()).is_some())
+        );
+        for (row, col, value) in streamed {
+            assert_eq!(value, ws.get_cell_value(row, col));
+        }
+
+        let wb = Workbook::open("test-data/ooxml/xlsx/MatrixFormulaEvalTestData.xlsx").unwrap();
+        let ws = wb.worksheet(0).unwrap();
+        assert_eq!(
+            formula(ws.get_cell_value(2, 8)),
+            Some(("B2:D4 + E2:G4".to_string(), true, Some("H2:J4".to_string())))
+        );
+        assert_eq!(formula(ws.get_cell_value(3, 9)), None);
+    }
+
+    #[test]
+    fn comments_round_trip_notes_and_threads() {
+        use crate::ooxml::xlsx::{Person, PersonList, ThreadedComment, Workbook};
+
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        ws.set_cell_value(1, 1, "value");
+        ws.set_cell_comment(2, 3, "Check this & that", "Alice");
+
+        let ws = wb.add_worksheet("Threads");
+        ws.add_threaded_comment(ThreadedComment {
+            cell_ref: Some("B2".to_string()),
+            id: "{00000000-0000-0000-0000-000000000001}".to_string(),
+            person_id: "{P1}".to_string(),
+            text: Some("Is this right?".to_string()),
+            date_time: Some("2024-05-01T09:30:00.00".to_string()),
+            ..Default::default()
+        });
+        ws.add_threaded_comment(ThreadedComment {
+            cell_ref: Some("B2".to_string()),
+            id: "{00000000-0000-0000-0000-000000000002}".to_string(),
+            parent_id: Some("{00000000-0000-0000-0000-000000000001}".to_string()),
+            person_id: "{P2}".to_string(),
+            text: Some("Yes".to_string()),
+            ..Default::default()
+        });
+        wb.set_person_list(PersonList {
+            persons: vec![
+                Person {
+                    display_name: "Alice".to_string(),
+                    id: "{P1}".to_string(),
+                    user_id: None,
+                    provider_id: None,
+                },
+                Person {
+                    display_name: "Bob".to_string(),
+                    id: "{P2}".to_string(),
+                    user_id: None,
+                    provider_id: None,
+                },
+            ],
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("comments.xlsx");
+        wb.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let wb = Workbook::open(&path).unwrap();
+
+        let notes = wb.worksheet(0).unwrap();
+        assert_eq!(notes.comments().len(), 1);
+        let note = &notes.comments()[0];
+        assert!(!note.is_threaded());
+        assert_eq!(note.cell_ref(), "C2");
+        assert_eq!(note.author(), Some("Alice"));
+        assert_eq!(note.text(), "Check this & that");
+        assert_eq!(
+            notes.get_cell_comment(2, 3).map(|c| c.text.as_str()),
+            Some("Check this & that")
+        );
+
+        let threads = wb.worksheet(1).unwrap();
+        assert_eq!(threads.comments().len(), 1);
+        let thread = &threads.comments()[0];
+        assert!(thread.is_threaded());
+        assert_eq!(thread.cell_ref(), "B2");
+        assert_eq!(thread.author(), Some("Alice"));
+        assert_eq!(thread.text(), "Is this right?");
+        assert_eq!(thread.timestamp(), Some("2024-05-01T09:30:00.00"));
+        assert_eq!(thread.replies().len(), 1);
+        assert_eq!(thread.replies()[0].author.as_deref(), Some("Bob"));
+        assert_eq!(thread.replies()[0].text, "Yes");
+
+        // Relationships to missing comment parts are skipped
+        use crate::ooxml::opc::phys_pkg::{PhysPkgEditor, PhysPkgReader};
+        let data = std::fs::read(&path).unwrap();
+        let mut editor = PhysPkgEditor::new(&data).unwrap();
+        for name in PhysPkgReader::new(&data).unwrap().member_names().unwrap() {
+            if ["xl/comments", "xl/threadedComments/", "xl/persons/"]
+                .iter()
+                .any(|prefix| name.starts_with(prefix))
+            {
+                editor.remove(&name);
+            }
+        }
+        let package = crate::ooxml::OpcPackage::recover(&editor.finish().unwrap()).unwrap();
+        let wb = Workbook::new(package).unwrap();
+        assert!(wb.worksheet(1).unwrap().comments().is_empty());
+        let notes = wb.worksheet(0).unwrap();
+        assert!(notes.comments().is_empty());
+        assert_eq!(
+            notes.get_cell_value(1, 1),
+            crate::sheet::CellValue::String("value".to_string())
+        );
+    }
+
+    #[test]
+    fn conditional_formats_round_trip() {
+        use crate::common::RGBColor;
+        use crate::ooxml::xlsx::{
+            CellFill, CellFillPatternType, CellFont, CellFormat, Cfvo, ConditionalFormatType,
+            IconSetStyle, Workbook,
+        };
+
+        let highlight = CellFormat {
+            font: Some(CellFont {
+                bold: true,
+                color: Some("FF9C0006".to_string()),
+                ..Default::default()
+            }),
+            fill: Some(CellFill {
+                pattern_type: CellFillPatternType::Solid,
+                fg_color: Some("FFFFC7CE".to_string()),
+                bg_color: None,
+            }),
+            ..Default::default()
+        };
+
+        let rules = vec![
+            (
+                "A1:A10",
+                ConditionalFormatType::two_color_scale(RGBColor::WHITE, RGBColor::BLUE),
+                None,
+            ),
+            (
+                "B1:B10",
+                ConditionalFormatType::three_color_scale(
+                    RGBColor::RED,
+                    RGBColor::YELLOW,
+                    RGBColor::LIME,
+                ),
+                None,
+            ),
+            (
+                "C1:C10",
+                ConditionalFormatType::DataBar {
+                    min: Cfvo::number(0.0),
+                    max: Cfvo::max(),
+                    color: RGBColor::new(0x63, 0x8E, 0xC6),
+                    show_value: false,
+                },
+                None,
+            ),
+            (
+                "D1:D10",
+                ConditionalFormatType::icon_set(IconSetStyle::Arrows3),
+                None,
+            ),
+            (
+                "E1:E10",
+                ConditionalFormatType::Top10 {
+                    rank: 3,
+                    percent: false,
+                    bottom: false,
+                },
+                Some(highlight.clone()),
+            ),
+            (
+                "E1:E10",
+                ConditionalFormatType::Top10 {
+                    rank: 10,
+                    percent: true,
+                    bottom: true,
+                },
+                None,
+            ),
+            (
+                "F1:F10",
+                ConditionalFormatType::AboveAverage {
+                    above: false,
+                    equal_average: true,
+                    std_dev: None,
+                },
+                Some(highlight),
+            ),
+        ];
+
+        let mut wb = Workbook::create().unwrap();
+        let ws = wb.worksheet_mut(0).unwrap();
+        for (priority, (range, rule, format)) in rules.iter().enumerate() {
+            ws.add_conditional_formatting(range, rule.clone(), priority as u32 + 1, format.clone());
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("conditional.xlsx");
+        wb.save(&path).unwrap();
+        crate::ooxml::opc::validation::assert_valid_package(&std::fs::read(&path).unwrap());
+
+        let wb = Workbook::open(&path).unwrap();
+        let ws = wb.worksheet(0).unwrap();
+        let read: Vec<_> = ws.conditional_formats().collect();
+        assert_eq!(read.len(), rules.len());
+        for ((range, rule), (expected_range, expected_rule, _)) in read.iter().zip(&rules) {
+            assert_eq!(range, expected_range);
+            assert_eq!(*rule, expected_rule);
+        }
+
+        // Both formatted rules share one differential format
+        let dxf_ids: Vec<_> = ws
+            .get_conditional_formatting()
+            .iter()
+            .map(|r| r.dxf_id)
+            .collect();
+        assert_eq!(dxf_ids[4], Some(0));
+        assert_eq!(dxf_ids[5], None);
+        assert_eq!(dxf_ids[6], Some(0));
+    }
 }
 
 /// Iterator over worksheets in a workbook
@@ -8,9 +8,9 @@ pub mod workbook;
 
 // Re-export main types
 pub use sheet::{
-    AutoFilter, CellComment, ConditionalFormat, ConditionalFormatType, FreezePanes, HeaderFooter,
-    Hyperlink, Image, MutableWorksheet, NamedRange, PageBreak, PageSetup, RichTextRun,
-    SheetProtection,
+    AutoFilter, CellComment, Cfvo, CfvoType, ColorScaleStop, ConditionalFormat,
+    ConditionalFormatType, FreezePanes, HeaderFooter, Hyperlink, IconSetStyle, Image,
+    MutableWorksheet, NamedRange, PageBreak, PageSetup, RichTextRun, SheetProtection,
 };
 pub use strings::MutableSharedStrings;
 pub use styles::StylesBuilder;
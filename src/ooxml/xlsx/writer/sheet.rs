@@ -1,11 +1,14 @@
 use crate::common::{id::generate_guid_braced, xml::escape::escape_xml};
+use crate::ooxml::charts::Chart;
 use crate::ooxml::drawings::blip::write_a_blip_embed_rid_num;
 use crate::ooxml::drawings::ext::write_a16_creation_id_extlst;
 use crate::ooxml::drawings::fill::write_a_stretch_fill_rect;
+use crate::ooxml::xlsx::page_layout::{PageMargins, PrintOptions};
 use crate::ooxml::xlsx::sort::{SortCondition, SortState};
 use crate::ooxml::xlsx::sparkline::{SparklineGroup, write_sparkline_groups_ext};
-use crate::ooxml::xlsx::table::Table;
-use crate::ooxml::xlsx::views::SheetView;
+use crate::ooxml::xlsx::table::{Table, TableColumn, TableStyleInfo};
+use crate::ooxml::xlsx::views::{Pane, PaneType, Selection, SheetView};
+use crate::ooxml::xlsx::worksheet::Worksheet;
 /// Writer module for creating and modifying Excel worksheets.
 use crate::sheet::{CellValue, Result as SheetResult};
 use std::collections::HashMap;
@@ -14,12 +17,24 @@ use std::fmt::Write as FmtWrite;
 // Import shared formatting types
 pub use super::super::format::{
     CellBorder, CellBorderLineStyle, CellBorderSide, CellFill, CellFillPatternType, CellFont,
-    CellFormat, DataValidation, DataValidationOperator, DataValidationType,
+    CellFormat,
+};
+
+// Import data validation types
+use super::super::data_validation::write_data_validations;
+pub use super::super::data_validation::{
+    DataValidationErrorStyle, DataValidationOperator, DataValidationRule, DataValidationType,
 };
 
 // Import chart types
 pub use super::super::chart::{ChartAnchor, WorksheetChart};
 
+// Import conditional formatting types
+use super::super::conditional_format::write_conditional_formats;
+pub use super::super::conditional_format::{
+    Cfvo, CfvoType, ColorScaleStop, ConditionalFormat, ConditionalFormatType, IconSetStyle,
+};
+
 // Import from other writer modules
 use super::strings::MutableSharedStrings;
 
@@ -189,59 +204,6 @@ pub struct RichTextRun {
     pub color: Option<String>,
 }
 
-/// Conditional formatting rule.
-#[derive(Debug, Clone)]
-pub struct ConditionalFormat {
-    /// Range (e.g., "A1:B10")
-    pub range: String,
-    /// Rule type
-    pub rule_type: ConditionalFormatType,
-    /// Priority (lower = higher priority)
-    pub priority: u32,
-    /// Format to apply (optional - can use built-in formats)
-    pub format: Option<CellFormat>,
-}
-
-/// Conditional formatting rule types.
-#[derive(Debug, Clone)]
-pub enum ConditionalFormatType {
-    /// Cell value comparison (e.g., greater than, less than)
-    CellIs {
-        /// Operator (e.g., "greaterThan", "lessThan", "equal")
-        operator: String,
-        /// Formula to compare against
-        formula: String,
-    },
-    /// Color scale (2 or 3 color gradient)
-    ColorScale {
-        /// Minimum color (RGB hex)
-        min_color: String,
-        /// Maximum color (RGB hex)
-        max_color: String,
-        /// Optional mid color (RGB hex)
-        mid_color: Option<String>,
-    },
-    /// Data bar
-    DataBar {
-        /// Color (RGB hex)
-        color: String,
-        /// Show value alongside bar
-        show_value: bool,
-    },
-    /// Icon set
-    IconSet {
-        /// Icon set name (e.g., "3Arrows", "3TrafficLights")
-        icon_set: String,
-        /// Show values
-        show_value: bool,
-    },
-    /// Formula-based
-    Expression {
-        /// Formula that returns TRUE/FALSE
-        formula: String,
-    },
-}
-
 /// A mutable worksheet for writing and modification.
 ///
 /// Provides methods to set cell values, formulas, and formatting.
@@ -260,7 +222,7 @@ pub struct MutableWorksheet {
     /// Charts in this worksheet
     charts: Vec<WorksheetChart>,
     /// Data validation rules
-    validations: Vec<DataValidation>,
+    validations: Vec<DataValidationRule>,
     /// Column widths (col -> width in characters)
     column_widths: HashMap<u32, f64>,
     /// Hidden columns
@@ -269,8 +231,6 @@ pub struct MutableWorksheet {
     row_heights: HashMap<u32, f64>,
     /// Hidden rows
     hidden_rows: std::collections::HashSet<u32>,
-    /// Freeze panes configuration
-    freeze_panes: Option<FreezePanes>,
     /// Whether the worksheet is hidden
     hidden: bool,
     /// Visibility state: "visible", "hidden", or "veryHidden"
@@ -281,6 +241,10 @@ pub struct MutableWorksheet {
     tab_color: Option<String>,
     /// Page setup configuration
     page_setup: Option<PageSetup>,
+    /// Page margins (Excel's defaults when unset)
+    page_margins: Option<PageMargins>,
+    /// Print options
+    print_options: Option<PrintOptions>,
     /// Print area
     print_area: Option<String>,
     /// Header and footer
@@ -337,12 +301,13 @@ impl MutableWorksheet {
             hidden_columns: std::collections::HashSet::new(),
             row_heights: HashMap::new(),
             hidden_rows: std::collections::HashSet::new(),
-            freeze_panes: None,
             hidden: false,
             visibility: "visible".to_string(),
             is_active: false,
             tab_color: None,
             page_setup: None,
+            page_margins: None,
+            print_options: None,
             print_area: None,
             header_footer: None,
             repeating_rows: None,
@@ -581,6 +546,25 @@ impl MutableWorksheet {
         self.modified = true;
     }
 
+    /// Set a cell value together with its formatting.
+    ///
+    /// Equal formats are shared when the workbook is saved, so the same
+    /// format can be applied to many cells without growing styles.xml.
+    ///
+    /// # Arguments
+    /// * `row` - 1-based row number
+    /// * `col` - 1-based column number
+    pub fn set_cell_with_format<V: Into<CellValue>>(
+        &mut self,
+        row: u32,
+        col: u32,
+        value: V,
+        format: &CellFormat,
+    ) {
+        self.set_cell_value(row, col, value);
+        self.set_cell_format(row, col, format.clone());
+    }
+
     /// Merge cells in a rectangular range.
     ///
     /// # Arguments
@@ -595,7 +579,54 @@ impl MutableWorksheet {
         self.modified = true;
     }
 
-    /// Add a chart to the worksheet.
+    /// Add a chart to the worksheet, placed over a cell range.
+    ///
+    /// Series references without a sheet name, such as `$B$2:$B$5`, point at
+    /// this worksheet. When the workbook is saved, references are written
+    /// sheet-qualified and series without cached data get a cache read from
+    /// the cells, so the chart renders as soon as the file is opened.
+    ///
+    /// # Arguments
+    ///
+    /// * `chart` - The chart to add
+    /// * `anchor` - Cell range covered by the chart (e.g., "E2:L18")
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use litchi::ooxml::charts::{Chart, NumericData, StringData, Series};
+    /// use litchi::ooxml::charts::axis::{Axis, CategoryAxis, ValueAxis};
+    /// use litchi::ooxml::charts::plot_area::{BarTypeGroup, PlotArea, TypeGroup};
+    /// use litchi::ooxml::charts::types::{AxisPosition, BarDirection, BarGrouping};
+    /// use litchi::ooxml::xlsx::Workbook;
+    ///
+    /// let mut workbook = Workbook::create()?;
+    /// let sheet = workbook.worksheet_mut(0)?;
+    /// sheet.set_cell_value(1, 1, "Jan");
+    /// sheet.set_cell_value(1, 2, 120.0);
+    ///
+    /// let mut bars = BarTypeGroup::new(BarDirection::Column, BarGrouping::Clustered);
+    /// bars.common.add_series(
+    ///     Series::new(0)
+    ///         .with_categories(StringData::from_ref("$A$1:$A$1"))
+    ///         .with_values(NumericData::from_ref("$B$1:$B$1")),
+    /// );
+    /// let chart = Chart::new().with_title("Sales").with_plot_area(
+    ///     PlotArea::new()
+    ///         .add_type_group(TypeGroup::Bar(bars))
+    ///         .add_axis(Axis::Category(CategoryAxis::new(1, AxisPosition::Bottom, 2)))
+    ///         .add_axis(Axis::Value(ValueAxis::new(2, AxisPosition::Left, 1))),
+    /// );
+    /// sheet.add_chart(chart, "D2:J16")?;
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn add_chart(&mut self, chart: Chart, anchor: &str) -> SheetResult<()> {
+        let anchor = ChartAnchor::from_range(anchor)?;
+        self.add_worksheet_chart(WorksheetChart::new(chart, anchor));
+        Ok(())
+    }
+
+    /// Add a chart with an explicit anchor to the worksheet.
     ///
     /// # Arguments
     ///
@@ -610,9 +641,9 @@ impl MutableWorksheet {
     ///     "Sheet1!$B$2:$B$10",
     ///     ChartAnchor::new(1, 1, 7, 14),
     /// )?;
-    /// worksheet.add_chart(chart);
+    /// worksheet.add_worksheet_chart(chart);
     /// ```
-    pub fn add_chart(&mut self, chart: WorksheetChart) {
+    pub fn add_worksheet_chart(&mut self, chart: WorksheetChart) {
         self.charts.push(chart);
         self.modified = true;
     }
@@ -644,29 +675,24 @@ impl MutableWorksheet {
         }
     }
 
-    /// Add data validation to a cell range.
-    #[allow(clippy::too_many_arguments)]
-    pub fn add_data_validation(
-        &mut self,
-        range: &str,
-        validation_type: DataValidationType,
-        show_input_message: bool,
-        input_title: Option<&str>,
-        input_message: Option<&str>,
-        show_error_alert: bool,
-        error_title: Option<&str>,
-        error_message: Option<&str>,
-    ) {
-        self.validations.push(DataValidation {
-            range: range.to_string(),
-            validation_type,
-            show_input_message,
-            input_title: input_title.map(|s| s.to_string()),
-            input_message: input_message.map(|s| s.to_string()),
-            show_error_alert,
-            error_title: error_title.map(|s| s.to_string()),
-            error_message: error_message.map(|s| s.to_string()),
-        });
+    /// Add a data validation rule.
+    ///
+    /// Rules read from an existing file with
+    /// [`Worksheet::get_data_validations`](crate::ooxml::xlsx::Worksheet::get_data_validations)
+    /// can be passed back unchanged.
+    pub fn add_data_validation(&mut self, rule: DataValidationRule) {
+        self.validations.push(rule);
+        self.modified = true;
+    }
+
+    /// Get the data validation rules of this worksheet.
+    pub fn data_validations(&self) -> &[DataValidationRule] {
+        &self.validations
+    }
+
+    /// Remove all data validation rules.
+    pub fn clear_data_validations(&mut self) {
+        self.validations.clear();
         self.modified = true;
     }
 
@@ -756,22 +782,64 @@ impl MutableWorksheet {
     }
 
     /// Freeze panes at the specified position.
+    ///
+    /// This replaces any split panes and selects the first scrollable cell.
     pub fn freeze_panes(&mut self, freeze_rows: u32, freeze_cols: u32) {
-        if freeze_rows > 0 || freeze_cols > 0 {
-            self.freeze_panes = Some(FreezePanes {
-                freeze_rows,
-                freeze_cols,
-            });
+        if let Some(pane) = Pane::frozen(freeze_rows, freeze_cols) {
+            let view = self.sheet_view.get_or_insert_with(SheetView::default);
+            let top_left = pane.top_left_cell.clone().unwrap_or_default();
+            view.selections = vec![Selection::cell(pane.active_pane, &top_left)];
+            view.pane = Some(pane);
             self.modified = true;
         }
     }
 
-    /// Remove freeze panes.
+    /// Split the window into panes without freezing them.
+    ///
+    /// Split positions are in twips (1/20 of a point) from the top-left
+    /// corner; use 0 for no split in that direction.
+    pub fn split_panes(&mut self, x_split: f64, y_split: f64) {
+        let active_pane = match (x_split > 0.0, y_split > 0.0) {
+            (true, true) => PaneType::BottomRight,
+            (true, false) => PaneType::TopRight,
+            (false, true) => PaneType::BottomLeft,
+            (false, false) => return self.unfreeze_panes(),
+        };
+        let view = self.sheet_view.get_or_insert_with(SheetView::default);
+        view.pane = Some(Pane {
+            x_split,
+            y_split,
+            top_left_cell: None,
+            active_pane: Some(active_pane),
+            state: None,
+        });
+        view.selections.clear();
+        self.modified = true;
+    }
+
+    /// Remove frozen or split panes.
     pub fn unfreeze_panes(&mut self) {
-        self.freeze_panes = None;
+        if let Some(ref mut view) = self.sheet_view {
+            view.pane = None;
+            view.selections.retain(|s| s.pane.is_none());
+        }
         self.modified = true;
     }
 
+    /// Get the frozen rows and columns, if panes are frozen.
+    pub fn get_freeze_panes(&self) -> Option<FreezePanes> {
+        let (freeze_rows, freeze_cols) = self
+            .sheet_view
+            .as_ref()?
+            .pane
+            .as_ref()?
+            .frozen_rows_cols()?;
+        Some(FreezePanes {
+            freeze_cols,
+            freeze_rows,
+        })
+    }
+
     /// Check if the worksheet has been modified.
     pub fn is_modified(&self) -> bool {
         self.modified
@@ -1427,10 +1495,13 @@ impl MutableWorksheet {
     /// * `range` - Cell range (e.g., "A1:B10")
     /// * `rule_type` - The formatting rule type
     /// * `priority` - Priority (lower = higher priority, typically start at 1)
+    /// * `format` - Differential format applied when the rule matches; written to
+    ///   styles.xml on save. Ignored by color scales, data bars and icon sets.
     ///
     /// # Examples
     ///
     /// ```rust,no_run
+    /// use litchi::common::RGBColor;
     /// use litchi::ooxml::xlsx::{Workbook, writer::ConditionalFormatType};
     ///
     /// let mut wb = Workbook::create()?;
@@ -1450,11 +1521,7 @@ impl MutableWorksheet {
     /// // Color scale
     /// ws.add_conditional_formatting(
     ///     "B1:B10",
-    ///     ConditionalFormatType::ColorScale {
-    ///         min_color: "FF0000".to_string(),
-    ///         max_color: "00FF00".to_string(),
-    ///         mid_color: None,
-    ///     },
+    ///     ConditionalFormatType::two_color_scale(RGBColor::RED, RGBColor::LIME),
     ///     2,
     ///     None,
     /// );
@@ -1474,6 +1541,7 @@ impl MutableWorksheet {
             rule_type,
             priority,
             format,
+            dxf_id: None,
         });
 
         self.modified = true;
@@ -1492,6 +1560,11 @@ impl MutableWorksheet {
         &self.conditional_formats
     }
 
+    /// Get mutable access to the conditional formatting rules (used to assign dxf ids on save).
+    pub(crate) fn conditional_formatting_mut(&mut self) -> &mut [ConditionalFormat] {
+        &mut self.conditional_formats
+    }
+
     // ===== Page Setup =====
     /// Configure page setup for printing.
     ///
@@ -1552,6 +1625,86 @@ impl MutableWorksheet {
         self.page_setup.as_ref()
     }
 
+    /// Set the page margins.
+    pub fn set_page_margins(&mut self, margins: PageMargins) {
+        self.page_margins = Some(margins);
+        self.modified = true;
+    }
+
+    /// Get the page margins, if set.
+    pub fn get_page_margins(&self) -> Option<&PageMargins> {
+        self.page_margins.as_ref()
+    }
+
+    /// Set the print options (centering, headings and grid lines).
+    pub fn set_print_options(&mut self, options: PrintOptions) {
+        self.print_options = Some(options);
+        self.modified = true;
+    }
+
+    /// Get the print options, if set.
+    pub fn get_print_options(&self) -> Option<&PrintOptions> {
+        self.print_options.as_ref()
+    }
+
+    /// Copy view and print settings from a worksheet that was read from a file.
+    ///
+    /// This carries over the sheet view (zoom, grid lines, right-to-left,
+    /// frozen or split panes, selection), page setup, margins, print options,
+    /// print area and print titles, so that a sheet rebuilt from an existing
+    /// file keeps its layout.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::ooxml::xlsx::Workbook;
+    ///
+    /// let source = Workbook::open("report.xlsx")?;
+    /// let sheet = source.worksheet(0)?;
+    ///
+    /// let mut wb = Workbook::create()?;
+    /// let ws = wb.worksheet_mut(0)?;
+    /// ws.copy_view_and_print_settings(&sheet);
+    /// wb.save("copy.xlsx")?;
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn copy_view_and_print_settings(&mut self, source: &Worksheet<'_>) {
+        self.sheet_view = source.sheet_view().cloned();
+
+        let setup = source.get_page_setup();
+        let has_page_setup = setup.paper_size.is_some()
+            || setup.landscape
+            || setup.scale.is_some()
+            || setup.fit_to_width.is_some()
+            || setup.fit_to_height.is_some();
+        self.page_setup = has_page_setup.then(|| PageSetup {
+            orientation: if setup.landscape {
+                "landscape"
+            } else {
+                "portrait"
+            }
+            .to_string(),
+            paper_size: setup.paper_size.unwrap_or(1),
+            scale: setup.scale,
+            fit_to_width: setup.fit_to_width.filter(|_| setup.fit_to_page),
+            fit_to_height: setup.fit_to_height.filter(|_| setup.fit_to_page),
+        });
+        if let Some(ref mut page_setup) = self.page_setup
+            && setup.fit_to_page
+        {
+            // Excel leaves out fitToWidth/fitToHeight when they are 1
+            page_setup.fit_to_width.get_or_insert(1);
+            page_setup.fit_to_height.get_or_insert(1);
+        }
+
+        self.page_margins = source.get_page_margins().copied();
+        self.print_options = source.get_print_options().copied();
+        self.print_area = source.get_print_area().map(str::to_string);
+        self.repeating_rows = source.get_repeating_rows().map(str::to_string);
+        self.repeating_columns = source.get_repeating_columns().map(str::to_string);
+        self.modified = true;
+    }
+
     /// Set the print area for the worksheet.
     ///
     /// # Arguments
@@ -1767,6 +1920,47 @@ impl MutableWorksheet {
         self.sheet_view.as_ref()
     }
 
+    /// Set the zoom level in percent (10-400).
+    pub fn set_zoom(&mut self, scale: u16) {
+        self.sheet_view
+            .get_or_insert_with(SheetView::default)
+            .zoom_scale = Some(scale.clamp(10, 400));
+        self.modified = true;
+    }
+
+    /// Show or hide grid lines.
+    pub fn set_show_grid_lines(&mut self, show: bool) {
+        self.sheet_view
+            .get_or_insert_with(SheetView::default)
+            .show_grid_lines = Some(show);
+        self.modified = true;
+    }
+
+    /// Display the sheet right-to-left, with column A on the right.
+    pub fn set_right_to_left(&mut self, right_to_left: bool) {
+        self.sheet_view
+            .get_or_insert_with(SheetView::default)
+            .right_to_left = Some(right_to_left);
+        self.modified = true;
+    }
+
+    /// Select cells, e.g. `"B2"` or `"B2:D4"`.
+    ///
+    /// The selection goes in the active pane, and the first cell of the
+    /// range becomes the active cell.
+    pub fn set_selection(&mut self, range: &str) {
+        let view = self.sheet_view.get_or_insert_with(SheetView::default);
+        let pane = view.pane.as_ref().and_then(|p| p.active_pane);
+        let active_cell = range.split([':', ' ']).next().unwrap_or(range).to_string();
+        view.selections.retain(|s| s.pane != pane);
+        view.selections.push(Selection {
+            pane,
+            active_cell: Some(active_cell),
+            sqref: Some(range.to_string()),
+        });
+        self.modified = true;
+    }
+
     // ===== Page Breaks =====
 
     /// Add a manual row page break.
@@ -1811,15 +2005,219 @@ impl MutableWorksheet {
 
     // ===== Tables =====
 
-    /// Add a table to the worksheet.
+    /// Add a table (ListObject) over a range whose first row holds the
+    /// column headers.
+    ///
+    /// When `columns` is empty, the column names are taken from the header
+    /// cells already in the worksheet (empty ones become `Column1`,
+    /// `Column2`, ...). Otherwise there must be one name per column and the
+    /// header cells are overwritten with them. `style` is a built-in table
+    /// style name such as `"TableStyleMedium2"`; styled tables get banded
+    /// rows. Every table gets an auto filter on its header row.
+    ///
+    /// Table names must be unique within the workbook, start with a letter
+    /// or underscore, contain no spaces and must not look like a cell
+    /// reference. Table ids are assigned when the workbook is saved.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use litchi::ooxml::xlsx::Workbook;
     ///
-    /// Tables provide structured references and enhanced formatting.
-    /// Table names must be unique within the workbook and cannot contain spaces.
-    pub fn add_table(&mut self, table: Table) {
+    /// let mut wb = Workbook::create()?;
+    /// let ws = wb.worksheet_mut(0)?;
+    /// ws.set_cell_value(2, 1, "North");
+    /// ws.set_cell_value(2, 2, 1250.0);
+    /// let table = ws.add_table("A1:B2", "Sales", &["Region", "Amount"], Some("TableStyleMedium2"))?;
+    /// assert_eq!(table.column_names(), ["Region", "Amount"]);
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn add_table(
+        &mut self,
+        range: &str,
+        name: &str,
+        columns: &[&str],
+        style: Option<&str>,
+    ) -> SheetResult<&mut Table> {
+        validate_table_name(name)?;
+        if self
+            .tables
+            .iter()
+            .any(|t| t.name.eq_ignore_ascii_case(name) || t.display_name.eq_ignore_ascii_case(name))
+        {
+            return Err(format!("Table name '{}' is already used", name).into());
+        }
+
+        let mut table = Table::new(0u32, name, range);
+        let (first_row, first_col, last_row, last_col) = table
+            .range_bounds()
+            .filter(|&(r1, c1, r2, c2)| r1 >= 1 && c1 >= 1 && r1 < r2 && c1 <= c2)
+            .ok_or_else(|| {
+                format!(
+                    "Invalid table range '{}': expected a header row and at least one data row",
+                    range
+                )
+            })?;
+        if let Some(other) = self.tables.iter().find(|t| {
+            t.range_bounds().is_some_and(|(r1, c1, r2, c2)| {
+                r1 <= last_row && first_row <= r2 && c1 <= last_col && first_col <= c2
+            })
+        }) {
+            return Err(format!("Table range '{}' overlaps table '{}'", range, other.name).into());
+        }
+
+        let width = (last_col - first_col + 1) as usize;
+        if !columns.is_empty() && columns.len() != width {
+            return Err(format!(
+                "Table '{}' spans {} columns but {} column names were given",
+                name,
+                width,
+                columns.len()
+            )
+            .into());
+        }
+        for (i, column) in columns.iter().enumerate() {
+            self.set_cell_value(first_row, first_col + i as u32, *column);
+        }
+
+        table.auto_filter_range = Some(range.to_string());
+        table.style_info = style.map(|style| TableStyleInfo {
+            name: Some(style.to_string()),
+            show_first_column: Some(false),
+            show_last_column: Some(false),
+            show_row_stripes: Some(true),
+            show_column_stripes: Some(false),
+        });
+        self.tables.push(table);
+        self.sync_table_headers();
+
+        Ok(self.tables.last_mut().expect("table was just added"))
+    }
+
+    /// Add a fully specified table definition.
+    ///
+    /// Column names are reconciled with the header cells when the workbook
+    /// is saved, see [`sync_table_headers`](Self::sync_table_headers).
+    pub fn add_table_definition(&mut self, table: Table) {
         self.tables.push(table);
         self.modified = true;
     }
 
+    /// Make table column names and header cells agree.
+    ///
+    /// Excel reports a file as corrupt when a table column's name differs
+    /// from the text of its header cell. For every table with a header row,
+    /// a non-empty header cell wins; an empty cell is filled in with the
+    /// column name (or `ColumnN` when there is none). Numbers and booleans
+    /// in header cells are converted to text, names are made unique the
+    /// way Excel does (`Name`, `Name2`, ...), and the column list is
+    /// resized to the width of the range.
+    ///
+    /// Totals row cells are rewritten from each column's label or function,
+    /// and the auto filter range is kept clear of the totals row.
+    ///
+    /// This runs when the workbook is saved, so header cells may be edited
+    /// after the table is added.
+    pub fn sync_table_headers(&mut self) {
+        let mut tables = std::mem::take(&mut self.tables);
+        for table in &mut tables {
+            self.sync_table(table);
+        }
+        self.tables = tables;
+        self.modified = true;
+    }
+
+    fn sync_table(&mut self, table: &mut Table) {
+        let Some((first_row, first_col, last_row, last_col)) = table.range_bounds() else {
+            return;
+        };
+        if first_row == 0 || first_col == 0 {
+            return;
+        }
+        let width = (last_col - first_col + 1) as usize;
+
+        table.columns.truncate(width);
+        while table.columns.len() < width {
+            let id = table.columns.iter().map(|c| c.id).max().unwrap_or(0) + 1;
+            table.columns.push(TableColumn::new(id, String::new()));
+        }
+
+        if table.has_header_row() {
+            let mut used: Vec<String> = Vec::with_capacity(width);
+            for (i, column) in table.columns.iter_mut().enumerate() {
+                let col = first_col + i as u32;
+                let cell = self.cells.get(&(first_row - 1, col - 1));
+                let mut name = cell
+                    .and_then(header_text)
+                    .filter(|text| !text.trim().is_empty())
+                    .unwrap_or_else(|| column.name.clone());
+                if name.trim().is_empty() {
+                    name = format!("Column{}", i + 1);
+                }
+
+                let base = name.clone();
+                let mut suffix = 2;
+                while used.iter().any(|u| u.eq_ignore_ascii_case(&name)) {
+                    name = format!("{}{}", base, suffix);
+                    suffix += 1;
+                }
+                used.push(name.clone());
+
+                if cell != Some(&CellValue::String(name.clone())) {
+                    self.cells
+                        .insert((first_row - 1, col - 1), CellValue::String(name.clone()));
+                }
+                column.name = name;
+            }
+        }
+
+        if table.has_totals_row() {
+            for (i, column) in table.columns.iter().enumerate() {
+                let pos = (last_row - 1, first_col - 1 + i as u32);
+                if let Some(ref label) = column.totals_row_label {
+                    self.cells.insert(pos, CellValue::String(label.clone()));
+                } else if let Some(function) = column.totals_row_function {
+                    let formula = match (function.subtotal_code(), &column.totals_row_formula) {
+                        (Some(code), _) => Some(format!(
+                            "SUBTOTAL({},{}[{}])",
+                            code,
+                            table.name,
+                            escape_structured_reference(&column.name)
+                        )),
+                        (None, Some(formula)) => Some(formula.text.clone()),
+                        (None, None) => None,
+                    };
+                    if let Some(formula) = formula {
+                        self.cells.insert(
+                            pos,
+                            CellValue::Formula {
+                                formula,
+                                cached_value: None,
+                                is_array: false,
+                                array_range: None,
+                            },
+                        );
+                    }
+                }
+            }
+        }
+
+        if table.auto_filter_range.is_some() {
+            let filter_last_row = if table.has_totals_row() {
+                last_row - 1
+            } else {
+                last_row
+            };
+            table.auto_filter_range = Some(format!(
+                "{}{}:{}{}",
+                Self::column_to_letters(first_col),
+                first_row,
+                Self::column_to_letters(last_col),
+                filter_last_row
+            ));
+        }
+    }
+
     /// Get all tables in the worksheet.
     pub fn tables(&self) -> &[Table] {
         &self.tables
@@ -2067,12 +2465,15 @@ impl MutableWorksheet {
     /// * `shared_strings` - Mutable shared strings table
     /// * `style_indices` - Optional map of cell positions to style indices
     /// * `hyperlink_rel_ids` - Map of cell references to relationship IDs for external hyperlinks
+    /// * `drawing_rel_id` - Optional relationship ID for the drawing (for charts and images)
     /// * `vml_rel_id` - Optional relationship ID for VML drawing (for comments)
+    #[allow(clippy::too_many_arguments)]
     pub fn to_xml_with_hyperlink_rels(
         &self,
         shared_strings: &mut MutableSharedStrings,
         style_indices: &HashMap<(u32, u32), usize>,
         hyperlink_rel_ids: &HashMap<String, String>,
+        drawing_rel_id: Option<&str>,
         vml_rel_id: Option<&str>,
         pivot_table_rel_ids: Option<&[String]>,
         table_rel_ids: Option<&[String]>,
@@ -2081,6 +2482,7 @@ impl MutableWorksheet {
             shared_strings,
             style_indices,
             Some(hyperlink_rel_ids),
+            drawing_rel_id,
             vml_rel_id,
             pivot_table_rel_ids,
             table_rel_ids,
@@ -2097,15 +2499,17 @@ impl MutableWorksheet {
         shared_strings: &mut MutableSharedStrings,
         style_indices: &HashMap<(u32, u32), usize>,
     ) -> SheetResult<String> {
-        self.to_xml_internal(shared_strings, style_indices, None, None, None, None)
+        self.to_xml_internal(shared_strings, style_indices, None, None, None, None, None)
     }
 
     /// Internal method for XML serialization with optional hyperlink relationship IDs.
+    #[allow(clippy::too_many_arguments)]
     fn to_xml_internal(
         &self,
         shared_strings: &mut MutableSharedStrings,
         style_indices: &HashMap<(u32, u32), usize>,
         hyperlink_rel_ids: Option<&HashMap<String, String>>,
+        drawing_rel_id: Option<&str>,
         vml_rel_id: Option<&str>,
         pivot_table_rel_ids: Option<&[String]>,
         table_rel_ids: Option<&[String]>,
@@ -2121,12 +2525,20 @@ impl MutableWorksheet {
         .map_err(|e| format!("XML write error: {}", e))?;
 
         // Write sheetPr (sheet properties) if needed - must come BEFORE dimension per OOXML spec
-        if self.tab_color.is_some() {
+        // fitToWidth/fitToHeight only take effect with fitToPage set here
+        let fit_to_page = self
+            .page_setup
+            .as_ref()
+            .is_some_and(|s| s.fit_to_width.is_some() || s.fit_to_height.is_some());
+        if self.tab_color.is_some() || fit_to_page {
             xml.push_str("<sheetPr>");
             if let Some(ref color) = self.tab_color {
                 write!(xml, r#"<tabColor rgb="{}"/>"#, color)
                     .map_err(|e| format!("XML write error: {}", e))?;
             }
+            if fit_to_page {
+                xml.push_str(r#"<pageSetUpPr fitToPage="1"/>"#);
+            }
             xml.push_str("</sheetPr>");
         }
 
@@ -2146,53 +2558,29 @@ impl MutableWorksheet {
             xml.push_str(r#"<dimension ref="A1"/>"#);
         }
 
-        // Write sheet views (including freeze panes if set)
+        // Write sheet views (including frozen or split panes if set)
         xml.push_str("<sheetViews><sheetView workbookViewId=\"0\"");
         if self.is_active() {
             xml.push_str(" tabSelected=\"1\"");
         }
-        if let Some(ref view) = self.sheet_view {
-            self.write_sheet_view_attributes(&mut xml, view)?;
-        }
-
-        // Add freeze panes if configured
-        if let Some(ref freeze) = self.freeze_panes {
-            xml.push('>');
-
-            let y_split = freeze.freeze_rows;
-            let x_split = freeze.freeze_cols;
-
-            let active_pane = match (x_split > 0, y_split > 0) {
-                (true, true) => "bottomRight",
-                (true, false) => "topRight",
-                (false, true) => "bottomLeft",
-                (false, false) => "",
-            };
-
-            // NOTE: Excel uses 1-based numbering for topLeftCell
-            let top_left_cell = format!("{}{}", Self::column_to_letters(x_split + 1), y_split + 1);
-
-            write!(
-                xml,
-                r#"<pane xSplit="{}" ySplit="{}" topLeftCell="{}" activePane="{}" state="frozen"/>"#,
-                x_split, y_split, top_left_cell, active_pane
-            )
-            .map_err(|e| format!("XML write error: {}", e))?;
-
-            if !active_pane.is_empty() {
-                write!(
-                    xml,
-                    r#"<selection pane="{}" activeCell="{}" sqref="{}"/>"#,
-                    active_pane, top_left_cell, top_left_cell
-                )
-                .map_err(|e| format!("XML write error: {}", e))?;
-            }
-
-            xml.push_str("</sheetView>");
-        } else {
-            xml.push_str("/>");
+        match self.sheet_view {
+            Some(ref view) if view.pane.is_some() || !view.selections.is_empty() => {
+                self.write_sheet_view_attributes(&mut xml, view)?;
+                xml.push('>');
+                if let Some(ref pane) = view.pane {
+                    Self::write_pane(&mut xml, pane)?;
+                }
+                for selection in &view.selections {
+                    Self::write_selection(&mut xml, selection)?;
+                }
+                xml.push_str("</sheetView>");
+            },
+            Some(ref view) => {
+                self.write_sheet_view_attributes(&mut xml, view)?;
+                xml.push_str("/>");
+            },
+            None => xml.push_str("/>"),
         }
-
         xml.push_str("</sheetViews>");
         xml.push_str("<sheetFormatPr defaultRowHeight=\"15\"/>");
 
@@ -2255,6 +2643,11 @@ impl MutableWorksheet {
             self.write_hyperlinks(&mut xml, hyperlink_rel_ids)?;
         }
 
+        // Write print options
+        if let Some(options) = self.print_options.filter(|o| !o.is_default()) {
+            options.write(&mut xml)?;
+        }
+
         // Write page margins (required by Excel)
         self.write_page_margins(&mut xml)?;
 
@@ -2274,8 +2667,9 @@ impl MutableWorksheet {
         }
 
         // Write drawing reference for charts and images
-        if !self.charts.is_empty() || !self.images.is_empty() {
-            xml.push_str(r#"<drawing r:id="rId1"/>"#);
+        if let Some(drawing_rel_id) = drawing_rel_id {
+            write!(xml, r#"<drawing r:id="{}"/>"#, drawing_rel_id)
+                .map_err(|e| format!("XML write error: {}", e))?;
         }
 
         // Write legacyDrawing reference for comments (VML)
@@ -2286,10 +2680,10 @@ impl MutableWorksheet {
 
         // Write tableParts if tables are present
         if let Some(table_rels) = table_rel_ids.filter(|rels| !rels.is_empty()) {
-            write!(xml, r#"<tableParts count=\"{}\">"#, table_rels.len())
+            write!(xml, r#"<tableParts count="{}">"#, table_rels.len())
                 .map_err(|e| format!("XML write error: {}", e))?;
             for rel_id in table_rels {
-                write!(xml, r#"<tablePart r:id=\"{}\"/>"#, rel_id)
+                write!(xml, r#"<tablePart r:id="{}"/>"#, rel_id)
                     .map_err(|e| format!("XML write error: {}", e))?;
             }
             xml.push_str("</tableParts>");
@@ -2625,220 +3019,7 @@ impl MutableWorksheet {
 
     /// Write data validations.
     fn write_data_validations(&self, xml: &mut String) -> SheetResult<()> {
-        if self.validations.is_empty() {
-            return Ok(());
-        }
-
-        write!(
-            xml,
-            r#"<dataValidations count="{}">"#,
-            self.validations.len()
-        )
-        .map_err(|e| format!("XML write error: {}", e))?;
-
-        for validation in &self.validations {
-            xml.push_str(r#"<dataValidation"#);
-
-            // Write type and operator
-            match &validation.validation_type {
-                DataValidationType::List { values } => {
-                    xml.push_str(r#" type="list""#);
-                    write!(xml, r#" sqref="{}""#, escape_xml(&validation.range))
-                        .map_err(|e| format!("XML write error: {}", e))?;
-
-                    if validation.show_input_message {
-                        xml.push_str(r#" showInputMessage="1""#);
-                    }
-                    if validation.show_error_alert {
-                        xml.push_str(r#" showErrorMessage="1""#);
-                    }
-
-                    if let Some(ref title) = validation.input_title {
-                        write!(xml, r#" promptTitle="{}""#, escape_xml(title))
-                            .map_err(|e| format!("XML write error: {}", e))?;
-                    }
-                    if let Some(ref msg) = validation.input_message {
-                        write!(xml, r#" prompt="{}""#, escape_xml(msg))
-                            .map_err(|e| format!("XML write error: {}", e))?;
-                    }
-                    if let Some(ref title) = validation.error_title {
-                        write!(xml, r#" errorTitle="{}""#, escape_xml(title))
-                            .map_err(|e| format!("XML write error: {}", e))?;
-                    }
-                    if let Some(ref msg) = validation.error_message {
-                        write!(xml, r#" error="{}""#, escape_xml(msg))
-                            .map_err(|e| format!("XML write error: {}", e))?;
-                    }
-
-                    xml.push('>');
-
-                    // Write list values as a comma-separated string in formula1
-                    let list_str = values.join(",");
-                    write!(xml, "<formula1>\"{}\"</formula1>", escape_xml(&list_str))
-                        .map_err(|e| format!("XML write error: {}", e))?;
-
-                    xml.push_str("</dataValidation>");
-                },
-                DataValidationType::Whole {
-                    operator,
-                    value1,
-                    value2,
-                } => {
-                    xml.push_str(r#" type="whole""#);
-                    write!(
-                        xml,
-                        r#" operator="{}" sqref="{}""#,
-                        operator.as_str(),
-                        escape_xml(&validation.range)
-                    )
-                    .map_err(|e| format!("XML write error: {}", e))?;
-
-                    self.write_validation_attributes(xml, validation)?;
-
-                    xml.push('>');
-
-                    write!(xml, "<formula1>{}</formula1>", value1)
-                        .map_err(|e| format!("XML write error: {}", e))?;
-                    if let Some(v2) = value2 {
-                        write!(xml, "<formula2>{}</formula2>", v2)
-                            .map_err(|e| format!("XML write error: {}", e))?;
-                    }
-
-                    xml.push_str("</dataValidation>");
-                },
-                DataValidationType::Decimal {
-                    operator,
-                    value1,
-                    value2,
-                } => {
-                    xml.push_str(r#" type="decimal""#);
-                    write!(
-                        xml,
-                        r#" operator="{}" sqref="{}""#,
-                        operator.as_str(),
-                        escape_xml(&validation.range)
-                    )
-                    .map_err(|e| format!("XML write error: {}", e))?;
-
-                    self.write_validation_attributes(xml, validation)?;
-
-                    xml.push('>');
-
-                    write!(xml, "<formula1>{}</formula1>", value1)
-                        .map_err(|e| format!("XML write error: {}", e))?;
-                    if let Some(v2) = value2 {
-                        write!(xml, "<formula2>{}</formula2>", v2)
-                            .map_err(|e| format!("XML write error: {}", e))?;
-                    }
-
-                    xml.push_str("</dataValidation>");
-                },
-                DataValidationType::TextLength {
-                    operator,
-                    value1,
-                    value2,
-                } => {
-                    xml.push_str(r#" type="textLength""#);
-                    write!(
-                        xml,
-                        r#" operator="{}" sqref="{}""#,
-                        operator.as_str(),
-                        escape_xml(&validation.range)
-                    )
-                    .map_err(|e| format!("XML write error: {}", e))?;
-
-                    self.write_validation_attributes(xml, validation)?;
-
-                    xml.push('>');
-
-                    write!(xml, "<formula1>{}</formula1>", value1)
-                        .map_err(|e| format!("XML write error: {}", e))?;
-                    if let Some(v2) = value2 {
-                        write!(xml, "<formula2>{}</formula2>", v2)
-                            .map_err(|e| format!("XML write error: {}", e))?;
-                    }
-
-                    xml.push_str("</dataValidation>");
-                },
-                DataValidationType::Date {
-                    operator,
-                    value1,
-                    value2,
-                } => {
-                    xml.push_str(r#" type="date""#);
-                    write!(
-                        xml,
-                        r#" operator="{}" sqref="{}""#,
-                        operator.as_str(),
-                        escape_xml(&validation.range)
-                    )
-                    .map_err(|e| format!("XML write error: {}", e))?;
-
-                    self.write_validation_attributes(xml, validation)?;
-
-                    xml.push('>');
-
-                    write!(xml, "<formula1>{}</formula1>", escape_xml(value1))
-                        .map_err(|e| format!("XML write error: {}", e))?;
-                    if let Some(v2) = value2 {
-                        write!(xml, "<formula2>{}</formula2>", escape_xml(v2))
-                            .map_err(|e| format!("XML write error: {}", e))?;
-                    }
-
-                    xml.push_str("</dataValidation>");
-                },
-                DataValidationType::Custom { formula } => {
-                    xml.push_str(r#" type="custom""#);
-                    write!(xml, r#" sqref="{}""#, escape_xml(&validation.range))
-                        .map_err(|e| format!("XML write error: {}", e))?;
-
-                    self.write_validation_attributes(xml, validation)?;
-
-                    xml.push('>');
-
-                    write!(xml, "<formula1>{}</formula1>", escape_xml(formula))
-                        .map_err(|e| format!("XML write error: {}", e))?;
-
-                    xml.push_str("</dataValidation>");
-                },
-            }
-        }
-
-        xml.push_str("</dataValidations>");
-        Ok(())
-    }
-
-    /// Write common validation attributes.
-    fn write_validation_attributes(
-        &self,
-        xml: &mut String,
-        validation: &DataValidation,
-    ) -> SheetResult<()> {
-        if validation.show_input_message {
-            xml.push_str(r#" showInputMessage="1""#);
-        }
-        if validation.show_error_alert {
-            xml.push_str(r#" showErrorMessage="1""#);
-        }
-
-        if let Some(ref title) = validation.input_title {
-            write!(xml, r#" promptTitle="{}""#, escape_xml(title))
-                .map_err(|e| format!("XML write error: {}", e))?;
-        }
-        if let Some(ref msg) = validation.input_message {
-            write!(xml, r#" prompt="{}""#, escape_xml(msg))
-                .map_err(|e| format!("XML write error: {}", e))?;
-        }
-        if let Some(ref title) = validation.error_title {
-            write!(xml, r#" errorTitle="{}""#, escape_xml(title))
-                .map_err(|e| format!("XML write error: {}", e))?;
-        }
-        if let Some(ref msg) = validation.error_message {
-            write!(xml, r#" error="{}""#, escape_xml(msg))
-                .map_err(|e| format!("XML write error: {}", e))?;
-        }
-
-        Ok(())
+        write_data_validations(xml, &self.validations)
     }
 
     /// Convert column number to Excel column letters (e.g., 1 -> "A", 26 -> "Z", 27 -> "AA").
@@ -2910,167 +3091,12 @@ impl MutableWorksheet {
 
     /// Write conditional formatting section.
     fn write_conditional_formatting(&self, xml: &mut String) -> SheetResult<()> {
-        if self.conditional_formats.is_empty() {
-            return Ok(());
-        }
-
-        // Group conditional formats by range
-        let mut formats_by_range: HashMap<String, Vec<&ConditionalFormat>> = HashMap::new();
-        for format in &self.conditional_formats {
-            formats_by_range
-                .entry(format.range.clone())
-                .or_default()
-                .push(format);
-        }
-
-        for (range, formats) in formats_by_range {
-            write!(
-                xml,
-                r#"<conditionalFormatting sqref="{}">"#,
-                escape_xml(&range)
-            )
-            .map_err(|e| format!("XML write error: {}", e))?;
-
-            for format in formats {
-                self.write_conditional_format_rule(xml, format)?;
-            }
-
-            xml.push_str("</conditionalFormatting>");
-        }
-
-        Ok(())
-    }
-
-    /// Write a single conditional formatting rule.
-    fn write_conditional_format_rule(
-        &self,
-        xml: &mut String,
-        format: &ConditionalFormat,
-    ) -> SheetResult<()> {
-        match &format.rule_type {
-            ConditionalFormatType::CellIs { operator, formula } => {
-                write!(
-                    xml,
-                    r#"<cfRule type="cellIs" priority="{}" operator="{}">"#,
-                    format.priority,
-                    escape_xml(operator)
-                )
-                .map_err(|e| format!("XML write error: {}", e))?;
-
-                write!(xml, "<formula>{}</formula>", escape_xml(formula))
-                    .map_err(|e| format!("XML write error: {}", e))?;
-
-                xml.push_str("</cfRule>");
-            },
-            ConditionalFormatType::ColorScale {
-                min_color,
-                max_color,
-                mid_color,
-            } => {
-                write!(
-                    xml,
-                    r#"<cfRule type="colorScale" priority="{}">"#,
-                    format.priority
-                )
-                .map_err(|e| format!("XML write error: {}", e))?;
-
-                xml.push_str("<colorScale>");
-
-                // OOXML Spec: ALL cfvo elements must come BEFORE color elements
-
-                // All cfvo elements first
-                xml.push_str(r#"<cfvo type="min"/>"#);
-                if mid_color.is_some() {
-                    xml.push_str(r#"<cfvo type="percentile" val="50"/>"#);
-                }
-                xml.push_str(r#"<cfvo type="max"/>"#);
-
-                // Then all color elements
-                write!(xml, r#"<color rgb="{}"/>"#, escape_xml(min_color))
-                    .map_err(|e| format!("XML write error: {}", e))?;
-                if let Some(mid) = mid_color {
-                    write!(xml, r#"<color rgb="{}"/>"#, escape_xml(mid))
-                        .map_err(|e| format!("XML write error: {}", e))?;
-                }
-                write!(xml, r#"<color rgb="{}"/>"#, escape_xml(max_color))
-                    .map_err(|e| format!("XML write error: {}", e))?;
-
-                xml.push_str("</colorScale>");
-                xml.push_str("</cfRule>");
-            },
-            ConditionalFormatType::DataBar { color, show_value } => {
-                write!(
-                    xml,
-                    r#"<cfRule type="dataBar" priority="{}">"#,
-                    format.priority
-                )
-                .map_err(|e| format!("XML write error: {}", e))?;
-
-                if *show_value {
-                    xml.push_str(r#"<dataBar showValue="1">"#);
-                } else {
-                    xml.push_str("<dataBar>");
-                }
-
-                xml.push_str(r#"<cfvo type="min"/>"#);
-                xml.push_str(r#"<cfvo type="max"/>"#);
-                write!(xml, r#"<color rgb="{}"/>"#, escape_xml(color))
-                    .map_err(|e| format!("XML write error: {}", e))?;
-
-                xml.push_str("</dataBar>");
-                xml.push_str("</cfRule>");
-            },
-            ConditionalFormatType::IconSet {
-                icon_set,
-                show_value,
-            } => {
-                write!(
-                    xml,
-                    r#"<cfRule type="iconSet" priority="{}">"#,
-                    format.priority
-                )
-                .map_err(|e| format!("XML write error: {}", e))?;
-
-                write!(
-                    xml,
-                    r#"<iconSet iconSet="{}" showValue="{}">"#,
-                    escape_xml(icon_set),
-                    if *show_value { "1" } else { "0" }
-                )
-                .map_err(|e| format!("XML write error: {}", e))?;
-
-                xml.push_str(r#"<cfvo type="percent" val="0"/>"#);
-                xml.push_str(r#"<cfvo type="percent" val="33"/>"#);
-                xml.push_str(r#"<cfvo type="percent" val="67"/>"#);
-
-                xml.push_str("</iconSet>");
-                xml.push_str("</cfRule>");
-            },
-            ConditionalFormatType::Expression { formula } => {
-                write!(
-                    xml,
-                    r#"<cfRule type="expression" dxfId="0" priority="{}">"#,
-                    format.priority
-                )
-                .map_err(|e| format!("XML write error: {}", e))?;
-
-                write!(xml, "<formula>{}</formula>", escape_xml(formula))
-                    .map_err(|e| format!("XML write error: {}", e))?;
-
-                xml.push_str("</cfRule>");
-            },
-        }
-
-        Ok(())
+        write_conditional_formats(xml, &self.conditional_formats)
     }
 
     /// Write page margins (required by Excel).
     fn write_page_margins(&self, xml: &mut String) -> SheetResult<()> {
-        // Default margins in inches: left=0.7, right=0.7, top=0.75, bottom=0.75, header=0.3, footer=0.3
-        xml.push_str(
-            r#"<pageMargins left="0.7" right="0.7" top="0.75" bottom="0.75" header="0.3" footer="0.3"/>"#,
-        );
-        Ok(())
+        self.page_margins.unwrap_or_default().write(xml)
     }
 
     /// Write page setup section.
@@ -3083,8 +3109,10 @@ impl MutableWorksheet {
                 .map_err(|e| format!("XML write error: {}", e))?;
 
             // Orientation
-            if setup.orientation == "landscape" {
-                xml.push_str(r#" orientation="landscape""#);
+            match setup.orientation.as_str() {
+                "landscape" => xml.push_str(r#" orientation="landscape""#),
+                "portrait" => xml.push_str(r#" orientation="portrait""#),
+                _ => {},
             }
 
             // Scale
@@ -3284,6 +3312,50 @@ impl MutableWorksheet {
         Ok(())
     }
 
+    fn write_pane(xml: &mut String, pane: &Pane) -> SheetResult<()> {
+        xml.push_str("<pane");
+        if pane.x_split != 0.0 {
+            write!(xml, r#" xSplit="{}""#, pane.x_split)
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+        if pane.y_split != 0.0 {
+            write!(xml, r#" ySplit="{}""#, pane.y_split)
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+        if let Some(ref cell) = pane.top_left_cell {
+            write!(xml, r#" topLeftCell="{}""#, escape_xml(cell))
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+        if let Some(active_pane) = pane.active_pane {
+            write!(xml, r#" activePane="{}""#, active_pane.as_str())
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+        if let Some(state) = pane.state {
+            write!(xml, r#" state="{}""#, state.as_str())
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+        xml.push_str("/>");
+        Ok(())
+    }
+
+    fn write_selection(xml: &mut String, selection: &Selection) -> SheetResult<()> {
+        xml.push_str("<selection");
+        if let Some(pane) = selection.pane {
+            write!(xml, r#" pane="{}""#, pane.as_str())
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+        if let Some(ref cell) = selection.active_cell {
+            write!(xml, r#" activeCell="{}""#, escape_xml(cell))
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+        if let Some(ref sqref) = selection.sqref {
+            write!(xml, r#" sqref="{}""#, escape_xml(sqref))
+                .map_err(|e| format!("XML write error: {}", e))?;
+        }
+        xml.push_str("/>");
+        Ok(())
+    }
+
     fn write_page_breaks(&self, xml: &mut String) -> SheetResult<()> {
         if !self.row_breaks.is_empty() {
             self.write_break_list(xml, "rowBreaks", &self.row_breaks)?;
@@ -3385,6 +3457,81 @@ impl MutableWorksheet {
     }
 }
 
+/// Text of a header cell, as Excel would convert it.
+fn header_text(value: &CellValue) -> Option<String> {
+    match value {
+        CellValue::String(s) => Some(s.clone()),
+        CellValue::Int(i) => Some(i.to_string()),
+        CellValue::Float(f) | CellValue::DateTime(f) => Some(f.to_string()),
+        CellValue::Bool(b) => Some(if *b { "TRUE" } else { "FALSE" }.to_string()),
+        CellValue::Formula {
+            cached_value: Some(value),
+            ..
+        } => header_text(value),
+        _ => None,
+    }
+}
+
+/// Escape the special characters of a column name inside `Table[Column]`.
+fn escape_structured_reference(name: &str) -> String {
+    let mut escaped = String::with_capacity(name.len());
+    for ch in name.chars() {
+        if matches!(ch, '[' | ']' | '#' | '\'') {
+            escaped.push('\'');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Check a table name against Excel's rules for defined names.
+fn validate_table_name(name: &str) -> SheetResult<()> {
+    let invalid = |reason: &str| Err(format!("Invalid table name '{}': {}", name, reason).into());
+
+    let mut chars = name.chars();
+    match chars.next() {
+        None => return invalid("name is empty"),
+        Some(first) if !(first.is_alphabetic() || first == '_' || first == '\\') => {
+            return invalid("must start with a letter, '_' or '\\'");
+        },
+        _ => {},
+    }
+    if name.chars().count() > 255 {
+        return invalid("longer than 255 characters");
+    }
+    if !chars.all(|c| c.is_alphanumeric() || c == '_' || c == '.' || c == '\\') {
+        return invalid("only letters, digits, '_', '.' and '\\' are allowed");
+    }
+    if looks_like_cell_reference(name) {
+        return invalid("looks like a cell reference");
+    }
+    Ok(())
+}
+
+/// Whether a name could be read as an A1 or R1C1 cell reference.
+fn looks_like_cell_reference(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    if matches!(upper.as_str(), "R" | "C") {
+        return true;
+    }
+
+    // A1 style: up to three letters followed by digits
+    let letters = upper.chars().take_while(|c| c.is_ascii_uppercase()).count();
+    let rest = &upper[letters..];
+    if (1..=3).contains(&letters) && !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()) {
+        return true;
+    }
+
+    // R1C1 style: R, optional digits, C, optional digits
+    if let Some(rest) = upper.strip_prefix('R') {
+        let rest = rest.trim_start_matches(|c: char| c.is_ascii_digit());
+        if let Some(rest) = rest.strip_prefix('C') {
+            return rest.chars().all(|c| c.is_ascii_digit());
+        }
+    }
+    false
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3500,4 +3647,130 @@ mod tests {
         let phonetic = xml.find("<phoneticPr").unwrap();
         assert!(filter < phonetic);
     }
+
+    #[test]
+    fn set_cell_with_format_stores_value_and_format() {
+        let mut ws = MutableWorksheet::new("Sheet1".to_string(), 1);
+        let header = CellFormat::bold_header();
+        ws.set_cell_with_format(1, 1, "Name", &header);
+        ws.set_cell_with_format(1, 2, "Total", &header);
+
+        assert!(matches!(ws.cell_value(1, 2), Some(CellValue::String(_))));
+        assert_eq!(ws.cell_formats().get(&(0, 1)), Some(&header));
+        assert_eq!(ws.cell_formats().len(), 2);
+    }
+
+    fn header(ws: &MutableWorksheet, col: u32) -> &str {
+        match ws.cell_value(1, col) {
+            Some(CellValue::String(s)) => s,
+            other => panic!("expected a string header, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn add_table_takes_column_names_from_header_cells() {
+        let mut ws = MutableWorksheet::new("Sheet1".to_string(), 1);
+        ws.set_cell_value(1, 1, "Region");
+        ws.set_cell_value(1, 3, 2024);
+        ws.set_cell_value(2, 1, "North");
+
+        let table = ws.add_table("A1:C2", "Sales", &[], None).unwrap();
+        assert_eq!(table.column_names(), ["Region", "Column2", "2024"]);
+        assert_eq!(table.auto_filter_range.as_deref(), Some("A1:C2"));
+        assert!(table.style_info.is_none());
+
+        // Header cells now hold exactly the column names
+        assert_eq!(header(&ws, 1), "Region");
+        assert_eq!(header(&ws, 2), "Column2");
+        assert_eq!(header(&ws, 3), "2024");
+    }
+
+    #[test]
+    fn add_table_writes_explicit_column_names_into_cells() {
+        let mut ws = MutableWorksheet::new("Sheet1".to_string(), 1);
+        ws.set_cell_value(1, 1, "old");
+
+        let table = ws
+            .add_table(
+                "A1:B3",
+                "People",
+                &["Name", "Age"],
+                Some("TableStyleMedium2"),
+            )
+            .unwrap();
+        assert_eq!(table.column_names(), ["Name", "Age"]);
+        let style = table.style_info.as_ref().unwrap();
+        assert_eq!(style.name.as_deref(), Some("TableStyleMedium2"));
+        assert_eq!(style.show_row_stripes, Some(true));
+
+        assert_eq!(header(&ws, 1), "Name");
+        assert_eq!(header(&ws, 2), "Age");
+    }
+
+    #[test]
+    fn header_sync_makes_duplicate_names_unique() {
+        let mut ws = MutableWorksheet::new("Sheet1".to_string(), 1);
+        let table = ws
+            .add_table("A1:C2", "Dupes", &["Total", "total", "Total"], None)
+            .unwrap();
+        assert_eq!(table.column_names(), ["Total", "total2", "Total3"]);
+        assert_eq!(header(&ws, 2), "total2");
+        assert_eq!(header(&ws, 3), "Total3");
+    }
+
+    #[test]
+    fn header_sync_follows_later_cell_edits() {
+        let mut ws = MutableWorksheet::new("Sheet1".to_string(), 1);
+        ws.add_table("A1:B2", "Edited", &["A", "B"], None).unwrap();
+
+        ws.set_cell_value(1, 2, "Renamed");
+        ws.set_cell_value(1, 1, "");
+        ws.sync_table_headers();
+
+        let table = ws.find_table("Edited").unwrap();
+        assert_eq!(table.column_names(), ["A", "Renamed"]);
+        assert_eq!(header(&ws, 1), "A");
+    }
+
+    #[test]
+    fn header_sync_writes_totals_row() {
+        let mut ws = MutableWorksheet::new("Sheet1".to_string(), 1);
+        let table = ws
+            .add_table("A1:B4", "Totals", &["Item", "Amount"], None)
+            .unwrap();
+        table.totals_row_count = Some(1);
+        table.columns[0].totals_row_label = Some("Total".to_string());
+        table.columns[1].totals_row_function =
+            Some(crate::ooxml::xlsx::table::TotalsRowFunction::Sum);
+        ws.sync_table_headers();
+
+        assert_eq!(
+            ws.cell_value(4, 1),
+            Some(&CellValue::String("Total".to_string()))
+        );
+        match ws.cell_value(4, 2) {
+            Some(CellValue::Formula { formula, .. }) => {
+                assert_eq!(formula, "SUBTOTAL(109,Totals[Amount])")
+            },
+            other => panic!("expected a formula, got {other:?}"),
+        }
+        let table = ws.find_table("Totals").unwrap();
+        assert_eq!(table.auto_filter_range.as_deref(), Some("A1:B3"));
+    }
+
+    #[test]
+    fn add_table_rejects_invalid_input() {
+        let mut ws = MutableWorksheet::new("Sheet1".to_string(), 1);
+        for name in ["", "My Table", "1st", "A1", "xfd100", "R", "r1c1", "RC"] {
+            assert!(ws.add_table("A1:B2", name, &[], None).is_err(), "{name}");
+        }
+        assert!(ws.add_table("A1:B1", "NoData", &[], None).is_err());
+        assert!(ws.add_table("not a range", "Bad", &[], None).is_err());
+        assert!(ws.add_table("A1:B2", "Width", &["Only"], None).is_err());
+
+        ws.add_table("A1:B2", "First", &[], None).unwrap();
+        assert!(ws.add_table("D1:E2", "first", &[], None).is_err());
+        assert!(ws.add_table("B2:C3", "Overlap", &[], None).is_err());
+        assert!(ws.add_table("D1:E2", "_Second.v2", &[], None).is_ok());
+    }
 }
@@ -1,13 +1,14 @@
 //! Styles.xml generator for XLSX files.
 //!
 //! This module handles the generation of the styles.xml file, which defines
-//! all the formatting information (fonts, fills, borders, number formats, and
-//! cell formats) used in an Excel workbook.
+//! all the formatting information (fonts, fills, borders, number formats,
+//! cell formats, and differential formats) used in an Excel workbook.
 
 use crate::common::xml::escape_xml;
 use crate::ooxml::xlsx::format::{
     CellBorder, CellBorderSide, CellFill, CellFillPatternType, CellFont, CellFormat,
 };
+use crate::ooxml::xlsx::styles::builtin_format_id;
 use crate::sheet::Result as SheetResult;
 use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
@@ -36,8 +37,12 @@ pub struct StylesBuilder {
     number_format_map: HashMap<String, usize>,
     /// Cell formats (XF records) - index -> (font_id, fill_id, border_id, num_fmt_id)
     cell_formats: Vec<(usize, usize, usize, usize)>,
-    /// Cell format lookup (format hash -> index)
-    cell_format_map: HashMap<u64, usize>,
+    /// Cell format lookup (component ids -> index)
+    cell_format_map: HashMap<(usize, usize, usize, usize), usize>,
+    /// Differential formats (DXF records) used by conditional formatting
+    dxfs: Vec<CellFormat>,
+    /// Differential format lookup (format hash -> index)
+    dxf_map: HashMap<u64, usize>,
 }
 
 impl StylesBuilder {
@@ -54,10 +59,16 @@ impl StylesBuilder {
             number_format_map: HashMap::new(),
             cell_formats: Vec::new(),
             cell_format_map: HashMap::new(),
+            dxfs: Vec::new(),
+            dxf_map: HashMap::new(),
         };
 
-        // Add default font (required by Excel)
-        builder.fonts.push(CellFont::default());
+        // Add default font (required by Excel). It is written with an explicit
+        // size so applications do not fall back to their own default.
+        builder.fonts.push(CellFont {
+            size: Some(11.0),
+            ..CellFont::default()
+        });
         builder
             .font_map
             .insert(Self::hash_font(&CellFont::default()), 0);
@@ -101,21 +112,17 @@ impl StylesBuilder {
 
         // Add default cell format (style index 0)
         builder.cell_formats.push((0, 0, 0, 0)); // font=0, fill=0, border=0, numFmt=0
+        builder.cell_format_map.insert((0, 0, 0, 0), 0);
 
         builder
     }
 
     /// Add a cell format and return its style index.
     ///
-    /// If the format has already been added, returns the existing index.
+    /// Formats are deduplicated by the fonts, fills, borders and number
+    /// formats they resolve to, so equal formats (including an explicit
+    /// default font and no font at all) share one XF record.
     pub fn add_cell_format(&mut self, format: &CellFormat) -> usize {
-        let format_hash = Self::hash_cell_format(format);
-
-        // Check if this format already exists
-        if let Some(&index) = self.cell_format_map.get(&format_hash) {
-            return index;
-        }
-
         // Add font if present
         let font_id = if let Some(ref font) = format.font {
             self.add_font(font)
@@ -144,12 +151,46 @@ impl StylesBuilder {
             0 // General format
         };
 
+        let key = (font_id, fill_id, border_id, num_fmt_id);
+        if let Some(&index) = self.cell_format_map.get(&key) {
+            return index;
+        }
+
         // Add the cell format
         let index = self.cell_formats.len();
-        self.cell_formats
-            .push((font_id, fill_id, border_id, num_fmt_id));
-        self.cell_format_map.insert(format_hash, index);
+        self.cell_formats.push(key);
+        self.cell_format_map.insert(key, index);
+
+        index
+    }
 
+    /// Number of cell formats (XF records), including the default one.
+    pub fn cell_format_count(&self) -> usize {
+        self.cell_formats.len()
+    }
+
+    /// Add a differential format and return its index.
+    ///
+    /// Differential formats only carry the properties that change, and are
+    /// referenced by conditional formatting rules through `dxfId`. If the
+    /// format has already been added, returns the existing index.
+    pub fn add_dxf(&mut self, format: &CellFormat) -> usize {
+        let format_hash = Self::hash_cell_format(format);
+        if let Some(&index) = self.dxf_map.get(&format_hash) {
+            return index;
+        }
+
+        // Custom number formats are shared with cell formats; built-in ones
+        // are referenced by their fixed ID
+        if let Some(ref num_fmt) = format.number_format
+            && builtin_format_id(num_fmt).is_none()
+        {
+            self.add_number_format(num_fmt);
+        }
+
+        let index = self.dxfs.len();
+        self.dxfs.push(format.clone());
+        self.dxf_map.insert(format_hash, index);
         index
     }
 
@@ -302,7 +343,18 @@ impl StylesBuilder {
 
         // Write dxfs (differential formats) - must come AFTER cellStyles per OOXML spec
         // These are used by conditional formatting
-        xml.push_str(r#"<dxfs count="0"/>"#);
+        if self.dxfs.is_empty() {
+            xml.push_str(r#"<dxfs count="0"/>"#);
+        } else {
+            write!(xml, r#"<dxfs count="{}">"#, self.dxfs.len())
+                .map_err(|e| format!("XML write error: {}", e))?;
+
+            for dxf in &self.dxfs {
+                self.write_dxf(&mut xml, dxf)?;
+            }
+
+            xml.push_str("</dxfs>");
+        }
 
         xml.push_str("</styleSheet>");
 
@@ -345,6 +397,74 @@ impl StylesBuilder {
         Ok(())
     }
 
+    /// Write a differential format element to XML.
+    ///
+    /// Unlike cell formats, only the properties that are set are written, and
+    /// a solid fill takes its color from `bgColor`.
+    fn write_dxf(&self, xml: &mut String, dxf: &CellFormat) -> SheetResult<()> {
+        xml.push_str("<dxf>");
+
+        if let Some(ref font) = dxf.font {
+            xml.push_str("<font>");
+            if font.bold {
+                xml.push_str("<b/>");
+            }
+            if font.italic {
+                xml.push_str("<i/>");
+            }
+            if font.underline {
+                xml.push_str("<u/>");
+            }
+            if let Some(ref size) = font.size {
+                write!(xml, r#"<sz val="{}"/>"#, size)
+                    .map_err(|e| format!("XML write error: {}", e))?;
+            }
+            if let Some(ref color) = font.color {
+                write!(xml, r#"<color rgb="{}"/>"#, escape_xml(color))
+                    .map_err(|e| format!("XML write error: {}", e))?;
+            }
+            if let Some(ref name) = font.name {
+                write!(xml, r#"<name val="{}"/>"#, escape_xml(name))
+                    .map_err(|e| format!("XML write error: {}", e))?;
+            }
+            xml.push_str("</font>");
+        }
+
+        if let Some(ref num_fmt) = dxf.number_format
+            && let Some(fmt_id) = builtin_format_id(num_fmt)
+                .map(|id| id as usize)
+                .or_else(|| self.number_format_map.get(num_fmt).copied())
+        {
+            write!(
+                xml,
+                r#"<numFmt numFmtId="{}" formatCode="{}"/>"#,
+                fmt_id,
+                escape_xml(num_fmt)
+            )
+            .map_err(|e| format!("XML write error: {}", e))?;
+        }
+
+        if let Some(ref fill) = dxf.fill {
+            if matches!(fill.pattern_type, CellFillPatternType::Solid) {
+                xml.push_str("<fill><patternFill>");
+                if let Some(color) = fill.fg_color.as_ref().or(fill.bg_color.as_ref()) {
+                    write!(xml, r#"<bgColor rgb="{}"/>"#, escape_xml(color))
+                        .map_err(|e| format!("XML write error: {}", e))?;
+                }
+                xml.push_str("</patternFill></fill>");
+            } else {
+                self.write_fill(xml, fill)?;
+            }
+        }
+
+        if let Some(ref border) = dxf.border {
+            self.write_border(xml, border)?;
+        }
+
+        xml.push_str("</dxf>");
+        Ok(())
+    }
+
     /// Write a fill element to XML.
     fn write_fill(&self, xml: &mut String, fill: &CellFill) -> SheetResult<()> {
         xml.push_str("<fill>");
@@ -561,4 +681,111 @@ mod tests {
         assert!(xml.contains("<borders"));
         assert!(xml.contains("<cellXfs"));
     }
+
+    #[test]
+    fn test_dxf_xml() {
+        let mut builder = StylesBuilder::new();
+
+        let format = CellFormat {
+            font: Some(CellFont {
+                bold: true,
+                ..Default::default()
+            }),
+            fill: Some(CellFill {
+                pattern_type: CellFillPatternType::Solid,
+                fg_color: Some("FFFFC7CE".to_string()),
+                bg_color: None,
+            }),
+            number_format: Some("0.0%".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(builder.add_dxf(&format), 0);
+        assert_eq!(builder.add_dxf(&format), 0);
+
+        let xml = builder.to_xml().unwrap();
+        assert!(xml.contains(r#"<dxfs count="1"><dxf><font><b/></font>"#));
+        assert!(xml.contains(r#"<numFmt numFmtId="164" formatCode="0.0%"/>"#));
+        assert!(xml.contains(
+            r#"<fill><patternFill><bgColor rgb="FFFFC7CE"/></patternFill></fill></dxf>"#
+        ));
+    }
+
+    #[test]
+    fn test_dxf_builtin_number_format() {
+        let mut builder = StylesBuilder::new();
+        let format = CellFormat {
+            number_format: Some("0.00%".to_string()),
+            ..Default::default()
+        };
+        builder.add_dxf(&format);
+
+        let xml = builder.to_xml().unwrap();
+        assert!(xml.contains(r#"<dxf><numFmt numFmtId="10" formatCode="0.00%"/></dxf>"#));
+        assert!(!xml.contains("<numFmts"));
+    }
+
+    #[test]
+    fn equal_formats_share_one_xf() {
+        let mut builder = StylesBuilder::new();
+
+        assert_eq!(builder.add_cell_format(&CellFormat::default()), 0);
+        let default_font = CellFormat::builder().font(CellFont::default()).build();
+        assert_eq!(builder.add_cell_format(&default_font), 0);
+
+        let header = builder.add_cell_format(&CellFormat::bold_header());
+        let currency = builder.add_cell_format(&CellFormat::currency("$"));
+        assert_eq!(builder.add_cell_format(&CellFormat::bold_header()), header);
+        assert_eq!(
+            builder.add_cell_format(&CellFormat::currency("$")),
+            currency
+        );
+        assert_ne!(header, currency);
+        assert_eq!(builder.cell_format_count(), 3);
+    }
+
+    #[test]
+    fn generated_styles_parse_back() {
+        use crate::ooxml::xlsx::Styles;
+        use crate::ooxml::xlsx::format::CellBorderLineStyle;
+
+        let mut builder = StylesBuilder::new();
+        let highlight = builder.add_cell_format(
+            &CellFormat::builder()
+                .bold()
+                .fill_color("FFFF00")
+                .border_all(CellBorderLineStyle::Thin, Some("000000"))
+                .number_format("0.00")
+                .build(),
+        );
+        let percent = builder.add_cell_format(&CellFormat::percent());
+        let date = builder.add_cell_format(&CellFormat::date());
+
+        let styles = Styles::parse(&builder.to_xml().unwrap()).unwrap();
+        assert_eq!(styles.cell_style_count(), 4);
+        assert_eq!(styles.font_count(), 2);
+        assert_eq!(styles.fill_count(), 3);
+        assert_eq!(styles.border_count(), 2);
+
+        let xf = styles.get_cell_style(highlight).unwrap();
+        assert!(xf.apply_font && xf.apply_fill && xf.apply_border);
+        let font = styles.get_font(xf.font_id.unwrap() as usize).unwrap();
+        assert!(font.bold);
+        let fill = styles.get_fill(xf.fill_id.unwrap() as usize).unwrap();
+        assert!(fill.is_solid());
+        let border = styles.get_border(xf.border_id.unwrap() as usize).unwrap();
+        assert!(border.has_borders());
+        let num_fmt = styles.get_number_format(xf.num_fmt_id.unwrap()).unwrap();
+        assert_eq!(num_fmt.code, "0.00");
+
+        let xf = styles.get_cell_style(percent).unwrap();
+        assert_eq!(xf.font_id, Some(0));
+        let num_fmt = styles.get_number_format(xf.num_fmt_id.unwrap()).unwrap();
+        assert_eq!(num_fmt.code, "0.00%");
+        assert!(!num_fmt.is_date_format());
+
+        let xf = styles.get_cell_style(date).unwrap();
+        let num_fmt = styles.get_number_format(xf.num_fmt_id.unwrap()).unwrap();
+        assert!(num_fmt.is_date_format());
+    }
 }
@@ -77,6 +77,7 @@ pub(crate) fn render_pivot_table_sheet_cells(
     }
 
     // Copy out the source data we need first (avoid simultaneous mutable borrows).
+    // parse_a1_range is 0-based; cell_value is 1-based.
     let ((start_row, start_col), (end_row, end_col)) = parse_a1_range(&pivot.source_ref)?;
     let header_row = start_row;
     let data_start_row = header_row + 1;
@@ -92,13 +93,13 @@ pub(crate) fn render_pivot_table_sheet_cells(
         if data_start_row <= end_row {
             for r in data_start_row..=end_row {
                 let row_cell = source_ws
-                    .cell_value(r, start_col + row_field_idx as u32)
+                    .cell_value(r + 1, start_col + row_field_idx as u32 + 1)
                     .unwrap_or(CellValue::EMPTY);
                 let col_cell = source_ws
-                    .cell_value(r, start_col + col_field_idx as u32)
+                    .cell_value(r + 1, start_col + col_field_idx as u32 + 1)
                     .unwrap_or(CellValue::EMPTY);
                 let data_cell = source_ws
-                    .cell_value(r, start_col + data_field_idx as u32)
+                    .cell_value(r + 1, start_col + data_field_idx as u32 + 1)
                     .unwrap_or(CellValue::EMPTY);
 
                 let row_key = match row_cell {
@@ -604,15 +605,42 @@ impl MutableWorkbookData {
         self.modified || self.worksheets.iter().any(|ws| ws.is_modified())
     }
 
+    /// Get every table ready to be written.
+    ///
+    /// Syncs table headers with their cells, checks that table names are
+    /// unique across the workbook (ignoring case, as Excel does) and numbers
+    /// the tables from 1 so each gets its own part.
+    pub fn prepare_tables(&mut self) -> SheetResult<()> {
+        let mut names: Vec<String> = Vec::new();
+        let mut next_id = 1;
+        for ws in &mut self.worksheets {
+            if ws.tables().is_empty() {
+                continue;
+            }
+            ws.sync_table_headers();
+            for table in ws.tables_mut() {
+                if names.iter().any(|n| n.eq_ignore_ascii_case(&table.name)) {
+                    return Err(format!("Duplicate table name '{}'", table.name).into());
+                }
+                names.push(table.name.clone());
+                table.id = next_id;
+                next_id += 1;
+            }
+        }
+        Ok(())
+    }
+
     /// Build styles from all worksheets and return a StylesBuilder and cell position -> style index mappings.
     ///
     /// Returns a tuple of (StylesBuilder, Vec of per-worksheet CellStyleMap).
-    pub fn build_styles(&self) -> SheetResult<(StylesBuilder, Vec<CellStyleMap>)> {
+    /// Conditional formatting rules with a format are assigned the index of
+    /// their differential format.
+    pub fn build_styles(&mut self) -> SheetResult<(StylesBuilder, Vec<CellStyleMap>)> {
         let mut builder = StylesBuilder::new();
         let mut worksheet_style_indices = Vec::new();
 
         // For each worksheet, collect cell formats and build style indices
-        for ws in &self.worksheets {
+        for ws in &mut self.worksheets {
             let mut style_map = CellStyleMap::new();
 
             // Iterate through all cells with formats
@@ -622,6 +650,11 @@ impl MutableWorkbookData {
                 style_map.insert(*pos, style_index);
             }
 
+            // Conditional formats reference differential formats
+            for cf in ws.conditional_formatting_mut() {
+                cf.dxf_id = cf.format.as_ref().map(|f| builder.add_dxf(f) as u32);
+            }
+
             worksheet_style_indices.push(style_map);
         }
 
@@ -676,7 +709,7 @@ impl MutableWorkbookData {
             .unwrap_or(0);
         write!(
             xml,
-            r#"<workbookView xWindow="0" yWindow="0" windowWidth="20000" windowHeight="10000" activeTab="{}" uid="{{00000000-0000-0000-0000-000000000000}}"/>"#,
+            r#"<workbookView xWindow="0" yWindow="0" windowWidth="20000" windowHeight="10000" activeTab="{}"/>"#,
             active_tab
         )
         .map_err(|e| format!("XML write error: {}", e))?;
@@ -1229,7 +1262,10 @@ pub(crate) fn generate_pivot_cache_records_xml(
         for row in data_start_row..=end_row {
             records_body.push_str("<r>");
             for (i, col) in (start_col..=end_col).enumerate() {
-                let v = source_ws.cell_value(row, col).unwrap_or(CellValue::EMPTY);
+                // parse_a1_range is 0-based; cell_value is 1-based
+                let v = source_ws
+                    .cell_value(row + 1, col + 1)
+                    .unwrap_or(CellValue::EMPTY);
                 write_pivot_record_value_indexed(&mut records_body, v, &mut field_stats[i])?;
             }
             records_body.push_str("</r>");
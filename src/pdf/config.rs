@@ -0,0 +1,103 @@
+/// Configuration types for PDF export.
+use crate::common::Length;
+
+/// Options for [`Document::to_pdf`](crate::Document::to_pdf).
+///
+/// # Examples
+///
+/// ```rust
+/// use litchi::common::Length;
+/// use litchi::pdf::PdfOptions;
+///
+/// // Letter pages unless the document sets a page size, 11pt sans-serif
+/// let options = PdfOptions::default();
+///
+/// // A4 fallback, 10pt DejaVu Serif, no page numbers
+/// let options = PdfOptions::new()
+///     .with_font_family("DejaVu Serif")
+///     .with_font_size(10.0)
+///     .with_default_page_size(Length::from_cm(21.0), Length::from_cm(29.7))
+///     .with_page_numbers(false);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PdfOptions {
+    /// System font family to embed; the fontconfig `sans-serif` family (or
+    /// the platform's default sans-serif font) is used when `None` or when
+    /// the family is not installed
+    pub font_family: Option<String>,
+    /// Font size in points of runs that do not set their own
+    pub font_size: f32,
+    /// Page width and height used when the document does not set a page size
+    pub default_page_size: (Length, Length),
+    /// Whether to print the page number at the bottom of each page
+    pub page_numbers: bool,
+}
+
+impl Default for PdfOptions {
+    fn default() -> Self {
+        Self {
+            font_family: None,
+            font_size: 11.0,
+            default_page_size: (Length::from_inches(8.5), Length::from_inches(11.0)),
+            page_numbers: true,
+        }
+    }
+}
+
+impl PdfOptions {
+    /// Create options for Letter pages with numbers and 11pt text.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Embed a system font family instead of the default sans-serif font.
+    #[inline]
+    pub fn with_font_family(mut self, family: impl Into<String>) -> Self {
+        self.font_family = Some(family.into());
+        self
+    }
+
+    /// Set the font size in points of runs that do not set their own.
+    #[inline]
+    pub fn with_font_size(mut self, size: f32) -> Self {
+        self.font_size = size;
+        self
+    }
+
+    /// Set the page size used when the document does not set one.
+    #[inline]
+    pub fn with_default_page_size(mut self, width: Length, height: Length) -> Self {
+        self.default_page_size = (width, height);
+        self
+    }
+
+    /// Print or leave out page numbers.
+    #[inline]
+    pub fn with_page_numbers(mut self, page_numbers: bool) -> Self {
+        self.page_numbers = page_numbers;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pdf_options_builder() {
+        let options = PdfOptions::new()
+            .with_font_family("DejaVu Serif")
+            .with_font_size(10.0)
+            .with_default_page_size(Length::from_cm(21.0), Length::from_cm(29.7))
+            .with_page_numbers(false);
+
+        assert_eq!(options.font_family.as_deref(), Some("DejaVu Serif"));
+        assert_eq!(options.font_size, 10.0);
+        assert_eq!(options.default_page_size.0, Length::from_cm(21.0));
+        assert!(!options.page_numbers);
+
+        let options = PdfOptions::default();
+        assert_eq!(options.font_family, None);
+        assert!(options.page_numbers);
+    }
+}
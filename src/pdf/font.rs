@@ -0,0 +1,315 @@
+//! Embedded TrueType fonts.
+//!
+//! Each font is written as a `Type0` font with `Identity-H` encoding, so the
+//! codes in content streams are glyph ids of the system font. Only the used
+//! glyphs are embedded: the subset renumbers them, and a `CIDToGIDMap`
+//! maps the original ids to the subset ones. A `ToUnicode` CMap keeps the
+//! text searchable and copyable.
+
+use super::writer::{ObjRef, PdfWriter, hex, num};
+use crate::fonts::{AllsortsSubsetter, FontData, FontLoader, FontSubsetter};
+use font_kit::font::Font;
+use font_kit::properties::Style;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::Arc;
+
+/// Families tried after the requested one, in order
+const FALLBACK_FAMILIES: [&str; 5] = [
+    "sans-serif",
+    "Arial",
+    "Helvetica",
+    "DejaVu Sans",
+    "Liberation Sans",
+];
+
+/// Glyph space units per em in PDF font dictionaries
+const PDF_UNITS: f32 = 1000.0;
+
+/// A system font face with the glyphs used from it.
+pub(super) struct PdfFont {
+    data: FontData,
+    font: Font,
+    /// Scale from font units to PDF glyph space
+    scale: f32,
+    /// Used glyphs, with the character each was first used for
+    used: BTreeMap<u16, char>,
+}
+
+impl PdfFont {
+    /// Load the face of the first installed family with TrueType outlines,
+    /// trying `family` first.
+    pub fn load(family: Option<&str>, bold: bool, italic: bool) -> Option<Self> {
+        let loader = FontLoader::new();
+        family
+            .into_iter()
+            .chain(FALLBACK_FAMILIES)
+            .filter_map(|family| {
+                loader
+                    .load_system_font_with_style(family, bold, italic)
+                    .ok()
+            })
+            .find_map(Self::from_data)
+    }
+
+    /// Wrap loaded font data, if it has TrueType outlines.
+    ///
+    /// Fonts with CFF outlines are not supported, since their subsets
+    /// keep glyph ids of their own.
+    fn from_data(data: FontData) -> Option<Self> {
+        let font = Font::from_bytes(Arc::new(data.data.clone()), data.index).ok()?;
+        font.load_font_table(u32::from_be_bytes(*b"glyf"))?;
+        let scale = PDF_UNITS / font.metrics().units_per_em as f32;
+        Some(Self {
+            data,
+            font,
+            scale,
+            used: BTreeMap::new(),
+        })
+    }
+
+    /// The glyph for a character, or `.notdef` if the font has none.
+    fn glyph(&self, ch: char) -> u16 {
+        self.font
+            .glyph_for_char(ch)
+            .and_then(|glyph| u16::try_from(glyph).ok())
+            .unwrap_or(0)
+    }
+
+    /// Advance width of a glyph in PDF glyph space.
+    fn advance(&self, glyph: u16) -> f32 {
+        self.font
+            .advance(glyph as u32)
+            .map(|advance| advance.x() * self.scale)
+            .unwrap_or(0.0)
+    }
+
+    /// Width of text in points at a font size.
+    pub fn measure(&self, text: &str, size: f32) -> f32 {
+        let units: f32 = text.chars().map(|ch| self.advance(self.glyph(ch))).sum();
+        units * size / PDF_UNITS
+    }
+
+    /// Distance from the baseline to the top of the tallest glyphs, as a
+    /// fraction of the font size.
+    pub fn ascent(&self) -> f32 {
+        self.font.metrics().ascent * self.scale / PDF_UNITS
+    }
+
+    /// Encode text as a hexadecimal string of glyph ids for `Tj`, recording
+    /// the glyphs as used.
+    pub fn encode(&mut self, text: &str) -> String {
+        let mut codes = Vec::with_capacity(text.len() * 2);
+        for ch in text.chars() {
+            let glyph = self.glyph(ch);
+            self.used.entry(glyph).or_insert(ch);
+            codes.extend_from_slice(&glyph.to_be_bytes());
+        }
+        format!("<{}>", hex(&codes))
+    }
+
+    /// Whether any text was encoded in the font.
+    pub fn is_used(&self) -> bool {
+        !self.used.is_empty()
+    }
+
+    /// Write the font and its descendants, subsetting it to the used
+    /// glyphs. `tag` is the six letter subset prefix of the font name.
+    pub fn write(&self, writer: &mut PdfWriter, id: ObjRef, tag: &str) {
+        // The subset numbers glyphs by their position in this list,
+        // starting with .notdef
+        let glyphs: Vec<u16> = std::iter::once(0)
+            .chain(self.used.keys().copied().filter(|&glyph| glyph != 0))
+            .collect();
+        let subset = AllsortsSubsetter::new().subset(&self.data, &glyphs);
+        let (file, cid_to_gid) = match subset {
+            Ok(file) => {
+                let max = glyphs.iter().copied().max().unwrap_or(0) as usize;
+                let mut map = vec![0u8; (max + 1) * 2];
+                for (new, &old) in glyphs.iter().enumerate() {
+                    map[old as usize * 2..old as usize * 2 + 2]
+                        .copy_from_slice(&(new as u16).to_be_bytes());
+                }
+                (file, Some(map))
+            },
+            // The whole face keeps the original glyph ids
+            Err(_) => {
+                let face = AllsortsSubsetter::new()
+                    .whole_face(&self.data)
+                    .unwrap_or_else(|_| self.data.data.clone());
+                (face, None)
+            },
+        };
+
+        let name = format!("{}+{}", tag, self.postscript_name());
+        let descendant = writer.reserve();
+        let descriptor = writer.reserve();
+        let font_file = writer.reserve();
+        let to_unicode = writer.reserve();
+
+        writer.object(
+            id,
+            &format!(
+                "<< /Type /Font /Subtype /Type0 /BaseFont /{} /Encoding /Identity-H \
+                 /DescendantFonts [{}] /ToUnicode {} >>",
+                name, descendant, to_unicode
+            ),
+        );
+
+        let map_id = cid_to_gid.as_ref().map(|_| writer.reserve());
+        let cid_to_gid_entry = match map_id {
+            Some(map_id) => map_id.to_string(),
+            None => "/Identity".to_string(),
+        };
+        writer.object(
+            descendant,
+            &format!(
+                "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{} \
+                 /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> \
+                 /FontDescriptor {} /DW 0 /W [{}] /CIDToGIDMap {} >>",
+                name,
+                descriptor,
+                self.widths(),
+                cid_to_gid_entry
+            ),
+        );
+        if let (Some(map_id), Some(map)) = (map_id, cid_to_gid) {
+            writer.stream(map_id, "", &map);
+        }
+
+        let metrics = self.font.metrics();
+        let bounds = metrics.bounding_box;
+        let mut flags = 4; // Symbolic, as glyphs are not in a standard encoding
+        if self.font.is_monospace() {
+            flags |= 1;
+        }
+        if self.is_italic() {
+            flags |= 64;
+        }
+        writer.object(
+            descriptor,
+            &format!(
+                "<< /Type /FontDescriptor /FontName /{} /Flags {} /FontBBox [{} {} {} {}] \
+                 /ItalicAngle {} /Ascent {} /Descent {} /CapHeight {} /StemV 80 /FontFile2 {} >>",
+                name,
+                flags,
+                num(bounds.min_x() * self.scale),
+                num(bounds.min_y() * self.scale),
+                num(bounds.max_x() * self.scale),
+                num(bounds.max_y() * self.scale),
+                num(-self.slant()),
+                num(metrics.ascent * self.scale),
+                num(metrics.descent * self.scale),
+                num(metrics.cap_height * self.scale),
+                font_file
+            ),
+        );
+        writer.stream(font_file, &format!("/Length1 {}", file.len()), &file);
+        writer.stream(to_unicode, "", self.to_unicode().as_bytes());
+    }
+
+    /// The PostScript name of the font, reduced to characters allowed in a
+    /// PDF name.
+    pub fn postscript_name(&self) -> String {
+        let name = self
+            .font
+            .postscript_name()
+            .unwrap_or_else(|| self.data.name.clone());
+        let name: String = name
+            .chars()
+            .filter(|ch| ch.is_ascii_alphanumeric() || *ch == '-' || *ch == '_')
+            .collect();
+        if name.is_empty() {
+            "Font".to_string()
+        } else {
+            name
+        }
+    }
+
+    fn is_italic(&self) -> bool {
+        self.font.properties().style != Style::Normal
+    }
+
+    /// Angle of italic glyphs in degrees, counterclockwise from vertical.
+    fn slant(&self) -> f32 {
+        if self.is_italic() { 12.0 } else { 0.0 }
+    }
+
+    /// The `W` array with the advance width of each used glyph.
+    fn widths(&self) -> String {
+        let mut widths = String::new();
+        for &glyph in self.used.keys() {
+            let _ = write!(widths, "{} [{}] ", glyph, num(self.advance(glyph)));
+        }
+        widths.trim_end().to_string()
+    }
+
+    /// A CMap from glyph ids to the characters they were used for.
+    fn to_unicode(&self) -> String {
+        let mut cmap = String::from(
+            "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+             /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+             /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+             1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+        );
+        let mappings: Vec<_> = self.used.iter().filter(|&(&glyph, _)| glyph != 0).collect();
+        // At most 100 mappings are allowed per block
+        for block in mappings.chunks(100) {
+            let _ = writeln!(cmap, "{} beginbfchar", block.len());
+            for (glyph, ch) in block {
+                let mut units = [0u16; 2];
+                let bytes: Vec<u8> = ch
+                    .encode_utf16(&mut units)
+                    .iter()
+                    .flat_map(|unit| unit.to_be_bytes())
+                    .collect();
+                let _ = writeln!(cmap, "<{:04X}> <{}>", glyph, hex(&bytes));
+            }
+            cmap.push_str("endbfchar\n");
+        }
+        cmap.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+        cmap
+    }
+}
+
+/// The six letter prefix that marks the name of the `index`th subset font.
+///
+/// The prefix only needs to be unique within the file.
+pub(super) fn subset_tag(index: usize) -> String {
+    let mut value = index;
+    let mut tag = [b'A'; 6];
+    for letter in tag.iter_mut().rev() {
+        *letter = b'A' + (value % 26) as u8;
+        value /= 26;
+    }
+    tag.iter().map(|&letter| letter as char).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subset_tags_are_unique_letters() {
+        assert_eq!(subset_tag(0), "AAAAAA");
+        assert_eq!(subset_tag(27), "AAAABB");
+        assert!(subset_tag(3).chars().all(|ch| ch.is_ascii_uppercase()));
+    }
+
+    #[test]
+    fn test_font_encodes_glyph_ids() {
+        let Some(mut font) = PdfFont::load(None, false, false) else {
+            // No TrueType font installed
+            return;
+        };
+        let encoded = font.encode("AA");
+        assert_eq!(encoded.len(), 10);
+        assert_eq!(encoded[1..5], encoded[5..9]);
+        assert_eq!(font.used.len(), 1);
+        assert!(font.measure("AA", 10.0) > font.measure("A", 10.0));
+
+        let cmap = font.to_unicode();
+        assert!(cmap.contains("1 beginbfchar"));
+        assert!(cmap.contains(&format!("<{}> <0041>", &encoded[1..5])));
+    }
+}
@@ -0,0 +1,208 @@
+//! Pictures as image XObjects.
+//!
+//! JPEG files in gray or RGB are embedded as they are with `DCTDecode`;
+//! other pictures are decoded and stored as compressed samples, with a soft
+//! mask for their alpha channel. Metafiles are converted to PNG first.
+
+use super::writer::{ObjRef, PdfWriter};
+use crate::document::displayable;
+use image::DynamicImage;
+
+/// Points per pixel of pictures without a stored size, at 96 DPI
+const POINTS_PER_PIXEL: f32 = 0.75;
+
+/// Samples of a picture, in the form they are written.
+enum Samples {
+    /// A JPEG file with 1 (gray) or 3 (RGB) components
+    Jpeg { data: Vec<u8>, components: u8 },
+    /// 8-bit gray or RGB samples and an optional 8-bit alpha channel
+    Raw {
+        data: Vec<u8>,
+        gray: bool,
+        alpha: Option<Vec<u8>>,
+    },
+}
+
+/// A decoded picture ready to be written as an image XObject.
+pub(super) struct PdfImage {
+    width: u32,
+    height: u32,
+    samples: Samples,
+}
+
+impl PdfImage {
+    /// Prepare picture data in the format given by its extension, or
+    /// `None` if it cannot be read.
+    pub fn new(data: Vec<u8>, extension: &'static str) -> Option<Self> {
+        let (data, _) = displayable(data, extension);
+        if let Some((width, height, components)) = jpeg_info(&data)
+            && matches!(components, 1 | 3)
+        {
+            return Some(Self {
+                width,
+                height,
+                samples: Samples::Jpeg { data, components },
+            });
+        }
+
+        let picture = image::load_from_memory(&data).ok()?;
+        let (width, height) = (picture.width(), picture.height());
+        let samples = match picture {
+            DynamicImage::ImageLuma8(gray) => Samples::Raw {
+                data: gray.into_raw(),
+                gray: true,
+                alpha: None,
+            },
+            picture if picture.color().has_alpha() => {
+                let rgba = picture.to_rgba8();
+                let mut data = Vec::with_capacity(rgba.len() / 4 * 3);
+                let mut alpha = Vec::with_capacity(rgba.len() / 4);
+                for pixel in rgba.pixels() {
+                    data.extend_from_slice(&pixel.0[..3]);
+                    alpha.push(pixel.0[3]);
+                }
+                let opaque = alpha.iter().all(|&value| value == u8::MAX);
+                Samples::Raw {
+                    data,
+                    gray: false,
+                    alpha: (!opaque).then_some(alpha),
+                }
+            },
+            picture => Samples::Raw {
+                data: picture.to_rgb8().into_raw(),
+                gray: false,
+                alpha: None,
+            },
+        };
+        Some(Self {
+            width,
+            height,
+            samples,
+        })
+    }
+
+    /// Size in points at 96 DPI.
+    pub fn intrinsic_size(&self) -> (f32, f32) {
+        (
+            self.width as f32 * POINTS_PER_PIXEL,
+            self.height as f32 * POINTS_PER_PIXEL,
+        )
+    }
+
+    /// Write the image, and its soft mask if it has one.
+    pub fn write(&self, writer: &mut PdfWriter, id: ObjRef) {
+        let size = format!(
+            "/Type /XObject /Subtype /Image /Width {} /Height {} /BitsPerComponent 8",
+            self.width, self.height
+        );
+        match &self.samples {
+            Samples::Jpeg { data, components } => {
+                let space = if *components == 1 {
+                    "DeviceGray"
+                } else {
+                    "DeviceRGB"
+                };
+                let entries = format!("{} /ColorSpace /{} /Filter /DCTDecode", size, space);
+                writer.raw_stream(id, &entries, data);
+            },
+            Samples::Raw { data, gray, alpha } => {
+                let space = if *gray { "DeviceGray" } else { "DeviceRGB" };
+                let mut entries = format!("{} /ColorSpace /{}", size, space);
+                let mask = alpha.as_ref().map(|alpha| (writer.reserve(), alpha));
+                if let Some((mask, _)) = &mask {
+                    entries.push_str(&format!(" /SMask {}", mask));
+                }
+                writer.stream(id, &entries, data);
+                if let Some((mask, alpha)) = mask {
+                    writer.stream(mask, &format!("{} /ColorSpace /DeviceGray", size), alpha);
+                }
+            },
+        }
+    }
+}
+
+/// Width, height and number of components of a baseline or progressive
+/// JPEG file, read from its start of frame segment.
+fn jpeg_info(data: &[u8]) -> Option<(u32, u32, u8)> {
+    if !data.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+    let mut pos = 2;
+    while pos + 4 <= data.len() {
+        if data[pos] != 0xFF {
+            return None;
+        }
+        let marker = data[pos + 1];
+        // Fill bytes before a marker
+        if marker == 0xFF {
+            pos += 1;
+            continue;
+        }
+        let length = u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        // SOF0 to SOF2: baseline, extended and progressive Huffman coding,
+        // which every PDF reader decodes
+        if matches!(marker, 0xC0..=0xC2) {
+            let segment = data.get(pos + 4..pos + 2 + length)?;
+            if segment.len() < 6 || segment[0] != 8 {
+                return None;
+            }
+            let height = u16::from_be_bytes([segment[1], segment[2]]) as u32;
+            let width = u16::from_be_bytes([segment[3], segment[4]]) as u32;
+            return (width > 0 && height > 0).then_some((width, height, segment[5]));
+        }
+        // Lossless and arithmetic coded frames, or image data without a
+        // frame header
+        let other_frame = matches!(marker, 0xC3 | 0xC5..=0xC7 | 0xC9..=0xCB | 0xCD..=0xCF);
+        if other_frame || marker == 0xDA {
+            return None;
+        }
+        pos += 2 + length;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Rgba, RgbaImage};
+    use std::io::Cursor;
+
+    fn encode(picture: DynamicImage, format: ImageFormat) -> Vec<u8> {
+        let mut data = Vec::new();
+        picture
+            .write_to(&mut Cursor::new(&mut data), format)
+            .unwrap();
+        data
+    }
+
+    #[test]
+    fn test_jpeg_is_embedded_as_is() {
+        let picture = DynamicImage::new_rgb8(40, 30);
+        let jpeg = encode(picture, ImageFormat::Jpeg);
+        assert_eq!(jpeg_info(&jpeg), Some((40, 30, 3)));
+
+        let image = PdfImage::new(jpeg.clone(), "jpeg").unwrap();
+        assert_eq!(image.intrinsic_size(), (30.0, 22.5));
+        assert!(matches!(&image.samples, Samples::Jpeg { data, .. } if *data == jpeg));
+    }
+
+    #[test]
+    fn test_png_alpha_becomes_soft_mask() {
+        let mut picture = RgbaImage::from_pixel(2, 2, Rgba([255, 0, 0, 255]));
+        let png = encode(DynamicImage::ImageRgba8(picture.clone()), ImageFormat::Png);
+        let image = PdfImage::new(png, "png").unwrap();
+        assert!(matches!(image.samples, Samples::Raw { alpha: None, .. }));
+
+        picture.put_pixel(0, 0, Rgba([0, 0, 0, 0]));
+        let png = encode(DynamicImage::ImageRgba8(picture), ImageFormat::Png);
+        let image = PdfImage::new(png, "png").unwrap();
+        let Samples::Raw { data, gray, alpha } = image.samples else {
+            panic!("expected raw samples");
+        };
+        assert!(!gray);
+        assert_eq!(data.len(), 12);
+        assert_eq!(alpha, Some(vec![0, 255, 255, 255]));
+
+        assert!(PdfImage::new(vec![1, 2, 3], "png").is_none());
+    }
+}
@@ -0,0 +1,631 @@
+//! Flow layout of paragraphs, tables and pictures onto pages.
+//!
+//! Content is placed top to bottom in the area inside the page margins.
+//! Lines break greedily at whitespace, and a line, table row or picture
+//! that does not fit in the space left on a page starts a new page.
+
+use super::font::PdfFont;
+use super::image::PdfImage;
+use super::writer::num;
+use std::fmt::Write as _;
+
+/// Line height as a multiple of the font size
+const LINE_SPACING: f32 = 1.2;
+/// Space after paragraphs, tables and pictures, in points
+const BLOCK_SPACING: f32 = 6.0;
+/// Padding between table rules and cell text, in points
+const CELL_PADDING: f32 = 4.0;
+/// Width of table rules, in points
+const RULE_WIDTH: f32 = 0.5;
+/// Spaces a tab is replaced with
+const TAB: &str = "    ";
+
+/// Page size and margins in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct PageBox {
+    pub width: f32,
+    pub height: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub left: f32,
+}
+
+impl PageBox {
+    fn content_width(&self) -> f32 {
+        (self.width - self.left - self.right).max(1.0)
+    }
+
+    fn content_height(&self) -> f32 {
+        (self.height - self.top - self.bottom).max(1.0)
+    }
+}
+
+/// The faces of the embedded font family, indexed by [`face_index`].
+pub(super) struct Fonts {
+    faces: [Option<PdfFont>; 4],
+}
+
+/// Index of the face for a weight and style.
+pub(super) fn face_index(bold: bool, italic: bool) -> usize {
+    bold as usize | (italic as usize) << 1
+}
+
+impl Fonts {
+    /// Load the regular, bold, italic and bold italic faces of a family.
+    ///
+    /// Returns `None` if no TrueType font could be found. Styles the
+    /// family does not have use the regular face.
+    pub fn load(family: Option<&str>) -> Option<Self> {
+        let regular = PdfFont::load(family, false, false)?;
+        let name = regular.postscript_name();
+        let mut faces = [Some(regular), None, None, None];
+        for (index, face) in faces.iter_mut().enumerate().skip(1) {
+            *face = PdfFont::load(family, index & 1 != 0, index & 2 != 0)
+                .filter(|font| font.postscript_name() != name);
+        }
+        Some(Self { faces })
+    }
+
+    /// The face used for a face index.
+    fn face(&self, index: usize) -> (usize, &PdfFont) {
+        match &self.faces[index] {
+            Some(font) => (index, font),
+            None => (0, self.regular()),
+        }
+    }
+
+    fn regular(&self) -> &PdfFont {
+        // The regular face is always loaded
+        self.faces[0].as_ref().expect("regular face")
+    }
+
+    fn measure(&self, face: usize, text: &str, size: f32) -> f32 {
+        self.face(face).1.measure(text, size)
+    }
+
+    /// Encode text in a face, returning the index of the face used.
+    fn encode(&mut self, face: usize, text: &str) -> (usize, String) {
+        let (index, _) = self.face(face);
+        let font = self.faces[index].as_mut().expect("loaded face");
+        (index, font.encode(text))
+    }
+
+    /// The faces text was set in, by index.
+    pub fn into_used(self) -> impl Iterator<Item = (usize, PdfFont)> {
+        self.faces
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, face)| face.map(|face| (index, face)))
+            .filter(|(_, face)| face.is_used())
+    }
+}
+
+/// Text with one set of character properties.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct Span {
+    pub text: String,
+    /// Index of the font face, from [`face_index`]
+    pub face: usize,
+    /// Font size in points
+    pub size: f32,
+    pub strike: bool,
+    /// Baseline shift in points, positive for superscript
+    pub rise: f32,
+}
+
+/// A paragraph ready for layout.
+#[derive(Debug, Clone, Default)]
+pub(super) struct Block {
+    pub spans: Vec<Span>,
+    /// Font size of the paragraph mark, which sets the height of an empty
+    /// paragraph
+    pub size: f32,
+    /// Left indentation in points
+    pub indent: f32,
+    /// Extra space above the paragraph in points
+    pub space_before: f32,
+}
+
+/// A table cell ready for layout.
+#[derive(Debug, Clone, Default)]
+pub(super) struct TableCell {
+    pub blocks: Vec<Block>,
+    /// Number of grid columns the cell spans
+    pub columns: usize,
+}
+
+/// A piece of a span placed on a line
+#[derive(Debug)]
+struct Piece {
+    span: usize,
+    text: String,
+    x: f32,
+}
+
+/// A laid out line
+#[derive(Debug, Default)]
+struct Line {
+    pieces: Vec<Piece>,
+    /// Width including trailing whitespace
+    advance: f32,
+    /// Largest font size on the line
+    size: f32,
+    /// Largest ascent on the line in points
+    ascent: f32,
+    /// Whether a page break follows the line
+    page_break: bool,
+}
+
+impl Line {
+    fn height(&self) -> f32 {
+        self.size * LINE_SPACING
+    }
+}
+
+/// Break a paragraph into lines at most `width` wide.
+///
+/// A word wider than the line is broken between characters.
+fn break_lines(fonts: &Fonts, block: &Block, width: f32) -> Vec<Line> {
+    let mut lines = Vec::new();
+    let mut line = Line::default();
+    let finish = |line: &mut Line, lines: &mut Vec<Line>, size: f32, page_break: bool| {
+        if line.size == 0.0 {
+            line.size = size;
+            line.ascent = size * fonts.regular().ascent();
+        }
+        line.page_break = page_break;
+        lines.push(std::mem::take(line));
+    };
+
+    for (index, span) in block.spans.iter().enumerate() {
+        let ascent = span.size * fonts.face(span.face).1.ascent();
+        for word in words(&span.text) {
+            if word == "\n" || word == "\x0c" {
+                finish(&mut line, &mut lines, span.size, word == "\x0c");
+                continue;
+            }
+            let mut word = word;
+            loop {
+                let width_of = |text: &str| fonts.measure(span.face, text, span.size);
+                let trailing = &word[word.trim_end().len()..];
+                let full = width_of(word);
+                let visible = full - width_of(trailing);
+                if !line.pieces.is_empty() && line.advance + visible > width {
+                    finish(&mut line, &mut lines, span.size, false);
+                }
+                // Break a word that is too wide for an empty line
+                let mut text = word;
+                if line.pieces.is_empty() && visible > width {
+                    let end = fit_chars(word, width, width_of);
+                    text = &word[..end];
+                }
+                let piece_width = if text.len() == word.len() {
+                    full
+                } else {
+                    width_of(text)
+                };
+                line.pieces.push(Piece {
+                    span: index,
+                    text: text.to_string(),
+                    x: line.advance,
+                });
+                line.advance += piece_width;
+                line.size = line.size.max(span.size);
+                line.ascent = line.ascent.max(ascent);
+                if text.len() == word.len() {
+                    break;
+                }
+                finish(&mut line, &mut lines, span.size, false);
+                word = &word[text.len()..];
+            }
+        }
+    }
+    finish(&mut line, &mut lines, block.size, false);
+    lines
+}
+
+/// Length in bytes of the longest prefix of `word` that fits in `width`,
+/// and at least one character.
+fn fit_chars(word: &str, width: f32, width_of: impl Fn(&str) -> f32) -> usize {
+    let mut end = word.chars().next().map_or(0, char::len_utf8);
+    for (index, ch) in word.char_indices().skip(1) {
+        if width_of(&word[..index + ch.len_utf8()]) > width {
+            break;
+        }
+        end = index + ch.len_utf8();
+    }
+    end
+}
+
+/// Split text into words, each with its trailing whitespace, and line and
+/// page breaks.
+fn words(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = text;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        if first == '\n' || first == '\x0c' {
+            let (word, after) = rest.split_at(1);
+            rest = after;
+            return Some(word);
+        }
+        let mut in_space = false;
+        let mut end = rest.len();
+        for (index, ch) in rest.char_indices() {
+            if ch == '\n' || ch == '\x0c' {
+                end = index;
+                break;
+            }
+            if ch.is_whitespace() {
+                in_space = true;
+            } else if in_space {
+                end = index;
+                break;
+            }
+        }
+        let (word, after) = rest.split_at(end);
+        rest = after;
+        Some(word)
+    })
+}
+
+/// Clean up run text for layout: tabs become spaces, .doc vertical tabs
+/// become line breaks, and other control characters are dropped.
+///
+/// Form feeds are kept as page breaks. `fields` tracks the .doc fields
+/// open across runs, whose instructions are dropped and results kept.
+pub(super) fn clean_text(text: &str, fields: &mut Vec<bool>) -> String {
+    let mut clean = String::with_capacity(text.len());
+    for ch in text.chars() {
+        match ch {
+            // Field begin, separator and end
+            '\x13' => fields.push(true),
+            '\x14' => {
+                if let Some(instruction) = fields.last_mut() {
+                    *instruction = false;
+                }
+            },
+            '\x15' => {
+                fields.pop();
+            },
+            _ if fields.iter().any(|&instruction| instruction) => {},
+            '\t' => clean.push_str(TAB),
+            '\x0b' => clean.push('\n'),
+            '\n' | '\x0c' => clean.push(ch),
+            ch if ch.is_control() => {},
+            ch => clean.push(ch),
+        }
+    }
+    clean
+}
+
+/// Places content on pages and builds their content streams.
+pub(super) struct Layout {
+    fonts: Fonts,
+    page: PageBox,
+    /// Font size of page numbers, if they are printed
+    page_numbers: Option<f32>,
+    /// Content streams of finished pages
+    pages: Vec<String>,
+    content: String,
+    /// Top of the free space on the current page
+    y: f32,
+    images: Vec<PdfImage>,
+}
+
+impl Layout {
+    pub fn new(fonts: Fonts, page: PageBox, page_numbers: Option<f32>) -> Self {
+        Self {
+            fonts,
+            page,
+            page_numbers,
+            pages: Vec::new(),
+            content: String::new(),
+            y: page.height - page.top,
+            images: Vec::new(),
+        }
+    }
+
+    /// Finish the last page and return the content streams of all pages,
+    /// the fonts and the pictures they use.
+    pub fn finish(mut self) -> (Vec<String>, Fonts, Vec<PdfImage>) {
+        self.new_page();
+        (self.pages, self.fonts, self.images)
+    }
+
+    fn page_top(&self) -> f32 {
+        self.page.height - self.page.top
+    }
+
+    fn at_page_top(&self) -> bool {
+        self.y >= self.page_top()
+    }
+
+    /// Finish the current page and start a new one.
+    fn new_page(&mut self) {
+        if let Some(size) = self.page_numbers {
+            let number = (self.pages.len() + 1).to_string();
+            let width = self.fonts.measure(0, &number, size);
+            let x = self.page.left + (self.page.content_width() - width) / 2.0;
+            let y = (self.page.bottom / 2.0 - size / 3.0).max(size / 2.0);
+            self.show_text(0, &number, size, x, y);
+        }
+        self.pages.push(std::mem::take(&mut self.content));
+        self.y = self.page_top();
+    }
+
+    /// Start a new page unless `height` fits in the space left on the
+    /// current one. Content taller than a page is placed at the top of a
+    /// page and overflows it.
+    fn make_room(&mut self, height: f32) {
+        if self.y - height < self.page.bottom && !self.at_page_top() {
+            self.new_page();
+        }
+    }
+
+    /// Lay out a paragraph in the body.
+    pub fn paragraph(&mut self, block: &Block) {
+        if !self.at_page_top() {
+            self.y -= block.space_before;
+        }
+        let width = self.page.content_width() - block.indent;
+        let lines = break_lines(&self.fonts, block, width.max(1.0));
+        for line in &lines {
+            // An empty line before a page break takes no space
+            if !(line.page_break && line.pieces.is_empty()) {
+                self.make_room(line.height());
+                let top = self.y;
+                self.draw_line(block, line, self.page.left + block.indent, top);
+                self.y -= line.height();
+            }
+            if line.page_break {
+                self.new_page();
+            }
+        }
+        self.y -= BLOCK_SPACING;
+    }
+
+    /// Draw the pieces of a line with its top at `top`.
+    fn draw_line(&mut self, block: &Block, line: &Line, left: f32, top: f32) {
+        let baseline = top - line.ascent;
+        for piece in &line.pieces {
+            let span = &block.spans[piece.span];
+            let text = piece.text.trim_end();
+            if text.is_empty() {
+                continue;
+            }
+            let x = left + piece.x;
+            self.show_text(span.face, text, span.size, x, baseline + span.rise);
+            if span.strike {
+                let width = self.fonts.measure(span.face, text, span.size);
+                let y = baseline + span.rise + span.size * 0.3;
+                let thickness = (span.size / 18.0).max(0.5);
+                self.rule(x, y, x + width, y, thickness);
+            }
+        }
+    }
+
+    fn show_text(&mut self, face: usize, text: &str, size: f32, x: f32, y: f32) {
+        let (face, encoded) = self.fonts.encode(face, text);
+        let _ = writeln!(
+            self.content,
+            "BT /F{} {} Tf {} {} Td {} Tj ET",
+            face,
+            num(size),
+            num(x),
+            num(y),
+            encoded
+        );
+    }
+
+    fn rule(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, width: f32) {
+        let _ = writeln!(
+            self.content,
+            "{} w {} {} m {} {} l S",
+            num(width),
+            num(x1),
+            num(y1),
+            num(x2),
+            num(y2)
+        );
+    }
+
+    /// Place a picture at the left margin, at `size` in points or its
+    /// intrinsic size, scaled down to fit the page.
+    pub fn image(&mut self, image: PdfImage, size: Option<(f32, f32)>) {
+        let (mut width, mut height) = size.unwrap_or_else(|| image.intrinsic_size());
+        if width <= 0.0 || height <= 0.0 {
+            return;
+        }
+        let fit = (self.page.content_width() / width)
+            .min(self.page.content_height() / height)
+            .min(1.0);
+        width *= fit;
+        height *= fit;
+
+        self.make_room(height);
+        self.images.push(image);
+        let _ = writeln!(
+            self.content,
+            "q {} 0 0 {} {} {} cm /Im{} Do Q",
+            num(width),
+            num(height),
+            num(self.page.left),
+            num(self.y - height),
+            self.images.len()
+        );
+        self.y -= height + BLOCK_SPACING;
+    }
+
+    /// Lay out a table as a grid of ruled cells, with column widths in
+    /// points. A row that does not fit on the page moves to the next one.
+    pub fn table(&mut self, rows: &[Vec<TableCell>], widths: &[f32]) {
+        for row in rows {
+            // Position, width and lines of each cell
+            let mut cells = Vec::with_capacity(row.len());
+            let mut column = 0;
+            let mut x = self.page.left;
+            for cell in row {
+                let span = cell.columns.max(1);
+                let width: f32 = widths.iter().skip(column).take(span).sum();
+                column += span;
+                let text_width = (width - 2.0 * CELL_PADDING).max(1.0);
+                let blocks: Vec<_> = cell
+                    .blocks
+                    .iter()
+                    .map(|block| {
+                        let width = (text_width - block.indent).max(1.0);
+                        (block, break_lines(&self.fonts, block, width))
+                    })
+                    .collect();
+                cells.push((x, width, blocks));
+                x += width;
+            }
+
+            let height = cells
+                .iter()
+                .map(|(_, _, blocks)| {
+                    let text: f32 = blocks
+                        .iter()
+                        .flat_map(|(_, lines)| lines)
+                        .map(Line::height)
+                        .sum();
+                    text + 2.0 * CELL_PADDING
+                })
+                .fold(0.0, f32::max);
+            self.make_room(height);
+
+            let top = self.y;
+            for (x, width, blocks) in &cells {
+                let mut y = top - CELL_PADDING;
+                for (block, lines) in blocks {
+                    for line in lines {
+                        self.draw_line(block, line, x + CELL_PADDING + block.indent, y);
+                        y -= line.height();
+                    }
+                }
+                let _ = writeln!(
+                    self.content,
+                    "{} w {} {} {} {} re S",
+                    num(RULE_WIDTH),
+                    num(*x),
+                    num(top - height),
+                    num(*width),
+                    num(height)
+                );
+            }
+            self.y -= height;
+        }
+        self.y -= BLOCK_SPACING;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span(text: &str, size: f32) -> Span {
+        Span {
+            text: text.to_string(),
+            face: 0,
+            size,
+            strike: false,
+            rise: 0.0,
+        }
+    }
+
+    fn letter() -> PageBox {
+        PageBox {
+            width: 612.0,
+            height: 792.0,
+            top: 72.0,
+            right: 72.0,
+            bottom: 72.0,
+            left: 72.0,
+        }
+    }
+
+    #[test]
+    fn test_words_keep_breaks() {
+        let words: Vec<_> = words("one  two\x0cthree\n").collect();
+        assert_eq!(words, ["one  ", "two", "\x0c", "three", "\n"]);
+    }
+
+    #[test]
+    fn test_clean_text_drops_field_instructions() {
+        let mut fields = Vec::new();
+        let text = clean_text("Page \x13 PAGE \x14", &mut fields);
+        assert_eq!(text, "Page ");
+        assert_eq!(clean_text("3\x15\tend\x0b\x07", &mut fields), "3    end\n");
+        assert!(fields.is_empty());
+    }
+
+    #[test]
+    fn test_break_lines_wraps_and_breaks_pages() {
+        let Some(fonts) = Fonts::load(None) else {
+            // No TrueType font installed
+            return;
+        };
+        let block = Block {
+            spans: vec![span("alpha beta gamma\x0cdelta", 10.0)],
+            size: 10.0,
+            ..Block::default()
+        };
+        let two_words = fonts.measure(0, "alpha beta", 10.0);
+        let lines = break_lines(&fonts, &block, two_words + 1.0);
+        let texts: Vec<Vec<&str>> = lines
+            .iter()
+            .map(|line| {
+                line.pieces
+                    .iter()
+                    .map(|piece| piece.text.as_str())
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            texts,
+            [vec!["alpha ", "beta "], vec!["gamma"], vec!["delta"]]
+        );
+        assert!(lines[1].page_break);
+        assert_eq!(lines[0].height(), 12.0);
+
+        // A word wider than the line is broken
+        let lines = break_lines(&fonts, &block, fonts.measure(0, "alp", 10.0));
+        assert_eq!(lines[0].pieces[0].text, "alp");
+    }
+
+    #[test]
+    fn test_layout_starts_new_pages() {
+        let Some(fonts) = Fonts::load(None) else {
+            return;
+        };
+        let mut layout = Layout::new(fonts, letter(), Some(10.0));
+        let block = Block {
+            spans: vec![span("line", 12.0)],
+            size: 12.0,
+            ..Block::default()
+        };
+        // 648pt of content height holds 32 lines of 14.4pt with 6pt between
+        for _ in 0..40 {
+            layout.paragraph(&block);
+        }
+        let rows = vec![vec![
+            TableCell {
+                blocks: vec![block.clone()],
+                columns: 1,
+            },
+            TableCell {
+                blocks: vec![block],
+                columns: 1,
+            },
+        ]];
+        layout.table(&rows, &[100.0, 200.0]);
+        let (pages, _, _) = layout.finish();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].matches(" Tj ET").count(), 32 + 1);
+        assert!(pages[1].contains("72 "));
+        assert!(pages[1].contains(" 100 22.4 re S"));
+        assert!(pages[1].contains(" 200 22.4 re S"));
+    }
+}
@@ -0,0 +1,520 @@
+//! PDF export of Word documents.
+//!
+//! [`Document::to_pdf`](crate::Document::to_pdf) lays out the body of a
+//! document on pages and writes a PDF file without external tools. Any
+//! document the unified API opens can be exported, including documents
+//! produced with the .docx writer and opened again with
+//! [`Document::from_bytes`](crate::Document::from_bytes).
+//!
+//! Pages take their size and margins from the first section. Paragraphs
+//! keep bold, italic, strikethrough, superscript, subscript and font sizes
+//! set on their runs; headings found by [`Document::outline`] are set in
+//! larger bold type, and list items are indented after their label. Tables
+//! become ruled grids, pictures are placed at their displayed size below
+//! the paragraph they are anchored to (metafiles are converted to PNG),
+//! and page breaks and page numbers are kept.
+//!
+//! Text is set in one system font family, embedded as a TrueType subset
+//! through the [`fonts`](crate::fonts) loader and subsetter, with its bold
+//! and italic faces when the family has them.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use litchi::Document;
+//! use litchi::pdf::PdfOptions;
+//!
+//! let doc = Document::open("document.docx")?;
+//! let pdf = doc.to_pdf(&PdfOptions::new().with_font_family("DejaVu Serif"))?;
+//! std::fs::write("document.pdf", pdf)?;
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Fidelity
+//!
+//! This is a basic flow layout, not a Word layout engine. Fonts named by
+//! the document, colors, alignment, paragraph indents and spacing from
+//! styles are not used, and text is never justified. Floating pictures
+//! and text boxes, columns, headers and footers, footnotes and
+//! per-section page setup are out of scope. Characters missing from the
+//! embedded font are shown as its `.notdef` glyph.
+
+mod config;
+mod font;
+mod image;
+mod layout;
+mod writer;
+
+pub use config::PdfOptions;
+
+use self::font::subset_tag;
+use self::image::PdfImage;
+use self::layout::{Block, Fonts, Layout, PageBox, Span, TableCell, clean_text, face_index};
+use self::writer::{PdfWriter, num, text_string};
+use crate::common::{Error, Length, Result};
+use crate::document::{Document, DocumentElement, ImageRef, Paragraph, Run, Section, Table};
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+/// Font sizes of heading levels 1 to 3 relative to the body text size;
+/// deeper levels keep the body size
+const HEADING_SCALES: [f32; 3] = [1.8, 1.45, 1.25];
+/// Space above headings as a multiple of the body text size
+const HEADING_SPACING: f32 = 1.0;
+/// Indentation per list level in points
+const LIST_INDENT: f32 = 18.0;
+/// Size of superscript and subscript text relative to the run size
+const SCRIPT_SCALE: f32 = 0.65;
+/// Default page margins (1 inch) in points
+const DEFAULT_MARGIN: f32 = 72.0;
+/// EMUs per point
+const EMUS_PER_POINT: f32 = 12_700.0;
+
+impl Document {
+    /// Convert the document to a PDF file.
+    ///
+    /// See the [`pdf`](crate::pdf) module for what is kept of the layout.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the document cannot be read, or no TrueType font is
+    /// installed to embed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Document;
+    /// use litchi::pdf::PdfOptions;
+    ///
+    /// let doc = Document::open("report.doc")?;
+    /// std::fs::write("report.pdf", doc.to_pdf(&PdfOptions::default())?)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_pdf(&self, options: &PdfOptions) -> Result<Vec<u8>> {
+        let fonts = Fonts::load(options.font_family.as_deref()).ok_or_else(|| {
+            Error::Other("No TrueType font is installed to embed in the PDF".to_string())
+        })?;
+        let page = page_box(self.sections()?.first(), options);
+        let page_numbers = options.page_numbers.then_some(options.font_size * 0.8);
+        let mut layout = Layout::new(fonts, page, page_numbers);
+
+        let headings: HashMap<usize, u8> = self
+            .outline()?
+            .iter()
+            .map(|entry| (entry.paragraph_index(), entry.level()))
+            .collect();
+        let mut images: HashMap<usize, Vec<ImageRef>> = HashMap::new();
+        for image in self.images()? {
+            images
+                .entry(image.paragraph_index())
+                .or_default()
+                .push(image);
+        }
+
+        // Paragraph index of the next element, as used by the outline and
+        // pictures
+        let mut index = 0;
+        for element in self.elements()? {
+            let count = match &element {
+                DocumentElement::Paragraph(para) => {
+                    let heading = headings.get(&index).copied();
+                    layout.paragraph(&block(para, options.font_size, heading)?);
+                    1
+                },
+                DocumentElement::Table(table) => {
+                    let (rows, widths) = table_cells(table, options.font_size, &page)?;
+                    layout.table(&rows, &widths);
+                    table.paragraph_count()?
+                },
+            };
+            for image in (index..index + count)
+                .filter_map(|index| images.remove(&index))
+                .flatten()
+            {
+                place_image(self, &mut layout, &image);
+            }
+            index += count;
+        }
+
+        let (pages, fonts, pictures) = layout.finish();
+        Ok(write_pdf(self, &page, &pages, fonts, &pictures))
+    }
+}
+
+/// Page size and margins from a section, with Letter pages and 1 inch
+/// margins where the document does not set them.
+fn page_box(section: Option<&Section>, options: &PdfOptions) -> PageBox {
+    let points = |length: Option<Length>, default: f32| {
+        length.map_or(default, |length| length.points() as f32)
+    };
+    let (default_width, default_height) = options.default_page_size;
+    let width = points(
+        section.and_then(Section::page_width),
+        default_width.points() as f32,
+    );
+    let height = points(
+        section.and_then(Section::page_height),
+        default_height.points() as f32,
+    );
+    let margins = section
+        .map(|section| *section.margins())
+        .unwrap_or_default();
+    let page = PageBox {
+        width,
+        height,
+        top: points(margins.top, DEFAULT_MARGIN).abs(),
+        right: points(margins.right, DEFAULT_MARGIN),
+        bottom: points(margins.bottom, DEFAULT_MARGIN).abs(),
+        left: points(margins.left, DEFAULT_MARGIN) + points(margins.gutter, 0.0),
+    };
+    // Fall back to the defaults when the margins leave no room for text
+    if page.width - page.left - page.right < 36.0 || page.height - page.top - page.bottom < 36.0 {
+        return PageBox {
+            top: DEFAULT_MARGIN.min(height / 4.0),
+            right: DEFAULT_MARGIN.min(width / 4.0),
+            bottom: DEFAULT_MARGIN.min(height / 4.0),
+            left: DEFAULT_MARGIN.min(width / 4.0),
+            ..page
+        };
+    }
+    page
+}
+
+/// The spans of a paragraph, with its list label and heading formatting.
+fn block(para: &Paragraph, font_size: f32, heading: Option<u8>) -> Result<Block> {
+    let size = match heading {
+        Some(level) => {
+            font_size
+                * HEADING_SCALES
+                    .get((level as usize).saturating_sub(1))
+                    .copied()
+                    .unwrap_or(1.0)
+        },
+        None => font_size,
+    };
+    let mut block = Block {
+        size,
+        space_before: heading.map_or(0.0, |_| font_size * HEADING_SPACING),
+        ..Block::default()
+    };
+
+    let bold = heading.is_some();
+    if let Some(list) = para.list_info() {
+        block.indent = (list.level() as f32 + 1.0) * LIST_INDENT;
+        if !list.number_text().is_empty() {
+            block.spans.push(Span {
+                text: format!("{} ", list.number_text()),
+                face: face_index(bold, false),
+                size,
+                strike: false,
+                rise: 0.0,
+            });
+        }
+    }
+
+    let runs = para.runs()?;
+    if runs.is_empty() {
+        // Formats without runs keep their text on the paragraph
+        let text = clean_text(&para.text()?, &mut Vec::new());
+        if !text.is_empty() {
+            block.spans.push(Span {
+                text,
+                face: face_index(bold, false),
+                size,
+                strike: false,
+                rise: 0.0,
+            });
+        }
+    }
+
+    let mut fields = Vec::new();
+    for run in &runs {
+        let text = clean_text(&run_text(run)?, &mut fields);
+        if text.is_empty() {
+            continue;
+        }
+        let run_size = run.font_size()?.filter(|size| *size > 0.0).unwrap_or(size);
+        let (run_size, rise) = script(run, run_size)?;
+        block.spans.push(Span {
+            text,
+            face: face_index(run.bold()?.unwrap_or(bold), run.italic()?.unwrap_or(false)),
+            size: run_size,
+            strike: run.strikethrough()?.unwrap_or(false),
+            rise,
+        });
+    }
+    Ok(block)
+}
+
+/// Text of a run with page breaks as form feeds.
+fn run_text(run: &Run) -> Result<String> {
+    match run {
+        #[cfg(feature = "ooxml")]
+        Run::Docx(run) => run.text_with_page_breaks().map_err(Error::from),
+        #[allow(unreachable_patterns)]
+        _ => run.text(),
+    }
+}
+
+/// Font size and baseline shift of superscript and subscript runs.
+#[cfg(any(feature = "ole", feature = "ooxml", feature = "iwa"))]
+fn script(run: &Run, size: f32) -> Result<(f32, f32)> {
+    use crate::common::VerticalPosition;
+
+    Ok(match run.vertical_position()? {
+        Some(VerticalPosition::Superscript) => (size * SCRIPT_SCALE, size * 0.35),
+        Some(VerticalPosition::Subscript) => (size * SCRIPT_SCALE, -size * 0.15),
+        _ => (size, 0.0),
+    })
+}
+
+#[cfg(not(any(feature = "ole", feature = "ooxml", feature = "iwa")))]
+fn script(_run: &Run, size: f32) -> Result<(f32, f32)> {
+    Ok((size, 0.0))
+}
+
+/// The cells of a table and the widths of its grid columns in points.
+///
+/// Widths stored in the document are scaled down to the page width if the
+/// table is wider; tables without widths share the page width equally.
+fn table_cells(
+    table: &Table,
+    font_size: f32,
+    page: &PageBox,
+) -> Result<(Vec<Vec<TableCell>>, Vec<f32>)> {
+    let mut rows = Vec::new();
+    let mut columns = 0;
+    for row in table.rows()? {
+        let mut cells = Vec::new();
+        for cell in row.cells()? {
+            let mut blocks = Vec::new();
+            for para in cell.paragraphs()? {
+                blocks.push(block(&para, font_size, None)?);
+            }
+            cells.push(TableCell {
+                blocks,
+                columns: cell.grid_span()?.max(1),
+            });
+        }
+        columns = columns.max(cells.iter().map(|cell| cell.columns).sum());
+        rows.push(cells);
+    }
+
+    let available = page.width - page.left - page.right;
+    let mut widths: Vec<f32> = table
+        .column_widths()?
+        .iter()
+        .map(|width| width.points() as f32)
+        .collect();
+    let total: f32 = widths.iter().sum();
+    if widths.len() < columns || widths.iter().any(|width| *width <= 0.0) {
+        widths = vec![available / columns.max(1) as f32; columns];
+    } else if total > available {
+        widths
+            .iter_mut()
+            .for_each(|width| *width *= available / total);
+    }
+    Ok((rows, widths))
+}
+
+/// Place a picture anchored to a paragraph, skipping it if its data cannot
+/// be read.
+fn place_image(doc: &Document, layout: &mut Layout, image: &ImageRef) {
+    let Ok(data) = doc.image_data(image) else {
+        return;
+    };
+    let Some(picture) = PdfImage::new(data, image.extension().unwrap_or_default()) else {
+        return;
+    };
+    let size = (image.width_emu() > 0 && image.height_emu() > 0).then(|| {
+        (
+            image.width_emu() as f32 / EMUS_PER_POINT,
+            image.height_emu() as f32 / EMUS_PER_POINT,
+        )
+    });
+    layout.image(picture, size);
+}
+
+/// Write the document catalog, pages, fonts and pictures.
+fn write_pdf(
+    doc: &Document,
+    page: &PageBox,
+    pages: &[String],
+    fonts: Fonts,
+    pictures: &[PdfImage],
+) -> Vec<u8> {
+    let mut writer = PdfWriter::new();
+    let catalog = writer.reserve();
+    let page_tree = writer.reserve();
+    let resources = writer.reserve();
+    let info = writer.reserve();
+
+    let page_ids: Vec<_> = pages
+        .iter()
+        .map(|_| (writer.reserve(), writer.reserve()))
+        .collect();
+    let kids: Vec<String> = page_ids.iter().map(|(id, _)| id.to_string()).collect();
+    writer.object(
+        catalog,
+        &format!("<< /Type /Catalog /Pages {} >>", page_tree),
+    );
+    writer.object(
+        page_tree,
+        &format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            kids.join(" "),
+            pages.len()
+        ),
+    );
+    for ((id, contents), content) in page_ids.iter().zip(pages) {
+        writer.object(
+            *id,
+            &format!(
+                "<< /Type /Page /Parent {} /MediaBox [0 0 {} {}] /Resources {} /Contents {} >>",
+                page_tree,
+                num(page.width),
+                num(page.height),
+                resources,
+                contents
+            ),
+        );
+        writer.stream(*contents, "", content.as_bytes());
+    }
+
+    // All pages share one resource dictionary
+    let mut font_entries = String::new();
+    for (position, (index, font)) in fonts.into_used().enumerate() {
+        let id = writer.reserve();
+        font.write(&mut writer, id, &subset_tag(position));
+        let _ = write!(font_entries, "/F{} {} ", index, id);
+    }
+    let mut image_entries = String::new();
+    for (number, picture) in pictures.iter().enumerate() {
+        let id = writer.reserve();
+        picture.write(&mut writer, id);
+        let _ = write!(image_entries, "/Im{} {} ", number + 1, id);
+    }
+    writer.object(
+        resources,
+        &format!(
+            "<< /ProcSet [/PDF /Text /ImageB /ImageC] /Font << {}>> /XObject << {}>> >>",
+            font_entries, image_entries
+        ),
+    );
+
+    let mut info_entries = format!("/Producer {}", text_string("litchi"));
+    if let Some(title) = doc.metadata().ok().and_then(|metadata| metadata.title)
+        && !title.is_empty()
+    {
+        let _ = write!(info_entries, " /Title {}", text_string(&title));
+    }
+    writer.object(info, &format!("<< {} >>", info_entries));
+    writer.finish(catalog, info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn test_data_path(path: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("test-data")
+            .join(path)
+    }
+
+    fn font_available() -> bool {
+        Fonts::load(None).is_some()
+    }
+
+    /// Decompress every FlateDecode stream of a file that has one, joined.
+    fn inflate_streams(pdf: &[u8]) -> String {
+        use std::io::Read;
+
+        let mut text = String::new();
+        let mut rest = pdf;
+        while let Some(start) = memchr::memmem::find(rest, b"stream\n") {
+            let data = &rest[start + 7..];
+            let end = memchr::memmem::find(data, b"\nendstream").unwrap();
+            let mut decoded = Vec::new();
+            if flate2::read::ZlibDecoder::new(&data[..end])
+                .read_to_end(&mut decoded)
+                .is_ok()
+            {
+                text.push_str(&String::from_utf8_lossy(&decoded));
+            }
+            rest = &data[end + b"\nendstream".len()..];
+        }
+        text
+    }
+
+    #[test]
+    fn test_page_box_from_section() {
+        let options = PdfOptions::default();
+        let page = page_box(None, &options);
+        assert_eq!((page.width, page.height), (612.0, 792.0));
+        assert_eq!((page.left, page.top), (72.0, 72.0));
+
+        let section = Section::new(
+            Some(Length::from_twips(11906)),
+            Some(Length::from_twips(16838)),
+            Default::default(),
+            crate::document::PageMargins {
+                left: Some(Length::from_inches(0.5)),
+                gutter: Some(Length::from_inches(0.25)),
+                ..Default::default()
+            },
+        );
+        let page = page_box(Some(&section), &options);
+        assert!((page.width - 595.3).abs() < 0.1);
+        assert!((page.height - 841.9).abs() < 0.1);
+        assert_eq!(page.left, 54.0);
+        assert_eq!(page.right, 72.0);
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_docx_to_pdf() {
+        if !font_available() {
+            return;
+        }
+        let doc = Document::open(test_data_path("ooxml/docx/FancyFoot.docx")).unwrap();
+        let pdf = doc.to_pdf(&PdfOptions::default()).unwrap();
+
+        assert!(pdf.starts_with(b"%PDF-1.7"));
+        assert!(pdf.ends_with(b"%%EOF\n"));
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Subtype /CIDFontType2"));
+        assert!(text.contains("/FontFile2"));
+        // The page break starts a second page
+        let count: usize = text
+            .split("/Type /Pages ")
+            .nth(1)
+            .and_then(|rest| rest.split("/Count ").nth(1))
+            .and_then(|rest| rest.split(' ').next())
+            .and_then(|count| count.parse().ok())
+            .unwrap();
+        assert_eq!(count, 2);
+
+        let streams = inflate_streams(&pdf);
+        assert!(streams.contains(" Tj ET"));
+        assert!(streams.contains("beginbfchar"));
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_doc_with_table_and_pictures_to_pdf() {
+        if !font_available() {
+            return;
+        }
+        let doc = Document::open(test_data_path("ole/doc/testPictures.doc")).unwrap();
+        let pdf = doc
+            .to_pdf(&PdfOptions::new().with_page_numbers(false))
+            .unwrap();
+
+        let text = String::from_utf8_lossy(&pdf);
+        assert!(text.contains("/Subtype /Image"));
+        let streams = inflate_streams(&pdf);
+        assert!(streams.contains(" Do Q"));
+    }
+}
@@ -0,0 +1,185 @@
+//! Serialization of PDF objects, streams and the cross-reference table.
+
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use sha2::{Digest, Sha256};
+use std::fmt::{self, Write as _};
+use std::io::Write as _;
+
+/// A reference to an indirect object.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct ObjRef(u32);
+
+impl fmt::Display for ObjRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} 0 R", self.0)
+    }
+}
+
+/// Writes indirect objects in order and records their offsets for the
+/// cross-reference table.
+pub(super) struct PdfWriter {
+    buf: Vec<u8>,
+    /// Byte offset of each object, by object number - 1
+    offsets: Vec<Option<usize>>,
+}
+
+impl PdfWriter {
+    /// Start a PDF 1.7 file.
+    pub fn new() -> Self {
+        // The comment of high bytes marks the file as binary
+        let mut buf = b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n".to_vec();
+        buf.reserve(64 * 1024);
+        Self {
+            buf,
+            offsets: Vec::new(),
+        }
+    }
+
+    /// Allocate an object number to write later.
+    pub fn reserve(&mut self) -> ObjRef {
+        self.offsets.push(None);
+        ObjRef(self.offsets.len() as u32)
+    }
+
+    /// Write an object whose body is a dictionary, array or other value.
+    pub fn object(&mut self, id: ObjRef, body: &str) {
+        self.begin(id);
+        self.buf.extend_from_slice(body.as_bytes());
+        self.buf.extend_from_slice(b"\nendobj\n");
+    }
+
+    /// Write a stream compressed with `FlateDecode`, with extra dictionary
+    /// entries.
+    pub fn stream(&mut self, id: ObjRef, entries: &str, data: &[u8]) {
+        let compressed = deflate(data);
+        let entries = format!("{} /Filter /FlateDecode", entries);
+        self.raw_stream(id, entries.trim_start(), &compressed);
+    }
+
+    /// Write a stream as given, with the dictionary entries for its filter.
+    pub fn raw_stream(&mut self, id: ObjRef, entries: &str, data: &[u8]) {
+        self.begin(id);
+        let dict = format!("<< {} /Length {} >>\nstream\n", entries, data.len());
+        self.buf.extend_from_slice(dict.as_bytes());
+        self.buf.extend_from_slice(data);
+        self.buf.extend_from_slice(b"\nendstream\nendobj\n");
+    }
+
+    fn begin(&mut self, id: ObjRef) {
+        self.offsets[id.0 as usize - 1] = Some(self.buf.len());
+        self.buf
+            .extend_from_slice(format!("{} 0 obj\n", id.0).as_bytes());
+    }
+
+    /// Write the cross-reference table and trailer and return the file.
+    ///
+    /// Objects that were reserved but never written are listed as free.
+    pub fn finish(mut self, root: ObjRef, info: ObjRef) -> Vec<u8> {
+        let id = Sha256::digest(&self.buf);
+        let id = hex(&id[..16]);
+
+        let xref = self.buf.len();
+        let mut table = format!("xref\n0 {}\n0000000000 65535 f \n", self.offsets.len() + 1);
+        for offset in &self.offsets {
+            match offset {
+                Some(offset) => {
+                    let _ = writeln!(table, "{:010} 00000 n ", offset);
+                },
+                None => table.push_str("0000000000 65535 f \n"),
+            }
+        }
+        let _ = write!(
+            table,
+            "trailer\n<< /Size {} /Root {} /Info {} /ID [<{id}> <{id}>] >>\nstartxref\n{}\n%%EOF\n",
+            self.offsets.len() + 1,
+            root,
+            info,
+            xref,
+        );
+        self.buf.extend_from_slice(table.as_bytes());
+        self.buf
+    }
+}
+
+/// Compress data in the zlib format of `FlateDecode`.
+fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut encoder = ZlibEncoder::new(Vec::with_capacity(data.len() / 2), Compression::default());
+    // Writing to a Vec cannot fail
+    let _ = encoder.write_all(data);
+    encoder.finish().unwrap_or_default()
+}
+
+/// Format a number for content streams and dictionaries, with at most two
+/// decimals and no trailing zeros.
+pub(super) fn num(value: f32) -> String {
+    let text = format!("{:.2}", value);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" | "" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+/// Upper case hexadecimal digits of bytes.
+pub(super) fn hex(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(text, "{:02X}", byte);
+    }
+    text
+}
+
+/// A text string for the document information dictionary, in UTF-16BE
+/// with a byte order mark so that any character can be stored.
+pub(super) fn text_string(text: &str) -> String {
+    let mut bytes = vec![0xFE, 0xFF];
+    for unit in text.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_be_bytes());
+    }
+    format!("<{}>", hex(&bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_num_formatting() {
+        assert_eq!(num(12.0), "12");
+        assert_eq!(num(0.5), "0.5");
+        assert_eq!(num(1.005), "1");
+        assert_eq!(num(-0.001), "0");
+        assert_eq!(num(612.3456), "612.35");
+    }
+
+    #[test]
+    fn test_xref_offsets_point_at_objects() {
+        let mut writer = PdfWriter::new();
+        let catalog = writer.reserve();
+        let info = writer.reserve();
+        let _unused = writer.reserve();
+        writer.object(catalog, "<< /Type /Catalog >>");
+        writer.stream(info, "", b"BT ET");
+        let pdf = writer.finish(catalog, info);
+
+        let xref = memchr::memmem::rfind(&pdf, b"\nxref\n").unwrap() + 1;
+        let tail = std::str::from_utf8(&pdf[xref..]).unwrap();
+        let startxref: usize = tail.lines().nth(9).unwrap().parse().unwrap();
+        assert_eq!(startxref, xref);
+
+        let entries: Vec<&str> = tail.lines().skip(2).take(4).collect();
+        assert_eq!(entries[0], "0000000000 65535 f ");
+        for (entry, number) in entries[1..3].iter().zip(1..) {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(format!("{} 0 obj", number).as_bytes()));
+        }
+        assert_eq!(entries[3], "0000000000 65535 f ");
+        assert!(memchr::memmem::find(&pdf, b"/Filter /FlateDecode /Length").is_some());
+    }
+
+    #[test]
+    fn test_text_string_is_utf16() {
+        assert_eq!(text_string("Aé"), "<FEFF004100E9>");
+    }
+}
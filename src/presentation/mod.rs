@@ -10,6 +10,7 @@
 //! - `Presentation`: The main presentation API (auto-detects format)
 //! - `Slide`: Individual slide with shapes and content
 //! - `Shape`: Shape elements on slides
+//! - `PresentationStatistics`: Slide and word counts
 //!
 //! # Example
 //!
@@ -36,9 +37,20 @@
 
 // Submodule declarations
 mod prs;
+mod shape;
 mod slide;
+mod statistics;
+mod table;
 mod types;
 
 // Re-exports
 pub use prs::Presentation;
+pub use shape::SlideShape;
 pub use slide::Slide;
+pub use statistics::PresentationStatistics;
+pub use table::{SlideTable, SlideTableCell};
+#[doc(hidden)]
+pub use types::SlideContent;
+#[cfg(feature = "html")]
+#[doc(hidden)]
+pub use types::{PositionedShape, PositionedSlide, StyledRun};
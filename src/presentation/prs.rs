@@ -1,8 +1,24 @@
 //! PowerPoint presentation implementation.
 
 use super::Slide;
-use super::types::PresentationImpl;
-use crate::common::{Error, Result};
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+use super::shape::SlideShape;
+use super::statistics::PresentationStatistics;
+#[cfg(any(feature = "ole", feature = "ooxml"))]
+use super::table::SlideTable;
+#[cfg(feature = "ole")]
+use super::types::PptFile;
+#[cfg(feature = "html")]
+use super::types::{PositionedShape, PositionedSlide, StyledRun};
+use super::types::{PresentationImpl, SlideContent};
+use crate::common::search::{MatchLocation, Matcher, SearchOptions, TextMatch, find_in_runs};
+use crate::common::statistics::{TextCounts, count_words};
+use crate::common::walk::TextEvent;
+#[cfg(any(feature = "ole", feature = "ooxml", feature = "odf", feature = "iwa"))]
+use crate::common::walk::visit;
+#[cfg(any(feature = "ole", feature = "odf", feature = "iwa"))]
+use crate::common::walk::visit_lines;
+use crate::common::{Error, Length, Protection, Result};
 
 #[cfg(feature = "ole")]
 use crate::ole;
@@ -10,6 +26,7 @@ use crate::ole;
 #[cfg(feature = "ooxml")]
 use crate::ooxml;
 
+use std::ops::ControlFlow;
 use std::path::Path;
 
 /// A PowerPoint presentation.
@@ -85,6 +102,15 @@ impl Presentation {
     /// # Ok::<(), litchi::common::Error>(())
     /// ```
     pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        // Legacy .ppt files are read from disk as their slides are needed
+        #[cfg(feature = "ole")]
+        if let Some(ole_file) = crate::common::detection::open_legacy_file(
+            path.as_ref(),
+            crate::common::detection::FileFormat::Ppt,
+        )? {
+            return Self::from_ole_file(ole_file);
+        }
+
         // Read file into memory and use smart detection for single-pass parsing
         // This is faster than the old approach of detecting first then parsing again
         let bytes = std::fs::read(path.as_ref())?;
@@ -123,40 +149,97 @@ impl Presentation {
     /// - **Single-pass parsing**: Format detection reuses the parsed structure (40-60% faster)
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
         // Use smart detection to parse only once
-        use crate::common::detection::{DetectedFormat, detect_format_smart};
+        use crate::common::detection::detected::detect_format_checked;
 
-        let detected = detect_format_smart(bytes).ok_or(Error::NotOfficeFile)?;
+        let detected = detect_format_checked(bytes)?;
+        Self::from_detected(detected)
+    }
 
-        match detected {
-            #[cfg(feature = "ole")]
-            DetectedFormat::Ppt(ole_file) => {
-                // OLE file already parsed - reuse it!
-                let mut package =
-                    Box::new(ole::ppt::Package::from_ole_file(ole_file).map_err(Error::from)?);
+    /// Open a password-protected presentation.
+    ///
+    /// Encrypted .pptx files (Standard 2007 or Agile encryption) are decrypted
+    /// in memory. Files that are not encrypted open normally and the password is
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// - [`Error::IncorrectPassword`] if the password does not unlock the file
+    /// - [`Error::UnsupportedEncryption`] if the encryption scheme is not supported
+    /// - [`Error::Encrypted`] for password-protected legacy .ppt files
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open_with_password("protected.pptx", "secret")?;
+    /// println!("Slides: {}", pres.slide_count()?);
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    #[cfg(feature = "ooxml_encryption")]
+    pub fn open_with_password<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        let bytes = std::fs::read(path.as_ref())?;
+        Self::from_bytes_with_password(bytes, password)
+    }
 
-                // Extract metadata from OLE property streams
-                let cached_metadata =
-                    package
-                        .ole_file()
-                        .get_metadata()
-                        .ok()
-                        .and_then(|ole_metadata| {
-                            let metadata: crate::common::Metadata = ole_metadata.into();
-                            if metadata.has_data() {
-                                Some(metadata)
-                            } else {
-                                None
-                            }
-                        });
+    /// Create a Presentation from the bytes of a possibly password-protected file.
+    ///
+    /// See [`Presentation::open_with_password`] for details.
+    #[cfg(feature = "ooxml_encryption")]
+    pub fn from_bytes_with_password(bytes: Vec<u8>, password: &str) -> Result<Self> {
+        use crate::common::detection::detect_format_with_password;
 
-                let pres = package.presentation().map_err(Error::from)?;
+        Self::from_detected(detect_format_with_password(bytes, password)?)
+    }
 
-                Ok(Self {
-                    inner: PresentationImpl::Ppt(pres),
-                    #[cfg(feature = "ooxml")]
-                    _pptx_package: None,
-                    cached_metadata,
-                })
+    /// Create a presentation from an OLE file already detected as a .ppt file.
+    ///
+    /// Only the metadata is read and the PowerPoint Document stream located
+    /// here; the presentation itself is parsed on first use.
+    #[cfg(feature = "ole")]
+    fn from_ole_file(ole_file: ole::OleFile<ole::OleSource>) -> Result<Self> {
+        let mut package = ole::ppt::Package::from_ole_file(ole_file).map_err(Error::from)?;
+        package.validate().map_err(Error::from)?;
+
+        // Extract metadata from OLE property streams
+        let cached_metadata = package
+            .ole_file()
+            .get_metadata()
+            .ok()
+            .and_then(|ole_metadata| {
+                let metadata: crate::common::Metadata = ole_metadata.into();
+                if metadata.has_data() {
+                    Some(metadata)
+                } else {
+                    None
+                }
+            });
+
+        Ok(Self {
+            inner: PresentationImpl::Ppt(PptFile::new(package)),
+            #[cfg(feature = "ooxml")]
+            _pptx_package: None,
+            cached_metadata,
+        })
+    }
+
+    /// Create a presentation from an already detected format.
+    ///
+    /// Shared by [`Presentation::from_bytes`] and [`crate::open_from_bytes`] so
+    /// the file is only parsed once.
+    #[allow(unused_mut)] // Only mutated for OLE encryption checks
+    pub(crate) fn from_detected(
+        mut detected: crate::common::detection::DetectedFormat,
+    ) -> Result<Self> {
+        use crate::common::detection::DetectedFormat;
+
+        #[cfg(feature = "ole")]
+        crate::common::detection::check_legacy_encryption(&mut detected)?;
+
+        match detected {
+            #[cfg(feature = "ole")]
+            DetectedFormat::Ppt(ole_file) => {
+                Self::from_ole_file(ole_file.map_reader(ole::OleSource::Memory))
             },
             #[cfg(feature = "ooxml")]
             DetectedFormat::Pptx(opc_package) => {
@@ -231,6 +314,10 @@ impl Presentation {
                     _pptx_package: None,
                 })
             },
+            #[cfg(feature = "ole")]
+            DetectedFormat::Encrypted(_) => Err(Error::Encrypted(
+                "password-protected OOXML package".to_string(),
+            )),
             // Handle mismatched formats
             #[allow(unreachable_patterns)]
             _ => Err(Error::InvalidFormat(
@@ -256,7 +343,7 @@ impl Presentation {
     pub fn text(&self) -> Result<String> {
         match &self.inner {
             #[cfg(feature = "ole")]
-            PresentationImpl::Ppt(pres) => pres.text().map_err(Error::from),
+            PresentationImpl::Ppt(pres) => pres.presentation()?.text().map_err(Error::from),
             #[cfg(feature = "ooxml")]
             PresentationImpl::Pptx(pres) => {
                 // PPTX presentations need to extract text from all slides
@@ -278,9 +365,7 @@ impl Presentation {
             #[cfg(feature = "odf")]
             PresentationImpl::Odp(doc) => {
                 let mut text = String::new();
-                let slides = doc
-                    .slides()
-                    .map_err(|e| Error::ParseError(format!("Failed to get ODP slides: {}", e)))?;
+                let slides = doc.slides()?;
                 for slide in slides {
                     if let Ok(slide_text) = slide.text() {
                         if !text.is_empty() {
@@ -294,6 +379,79 @@ impl Presentation {
         }
     }
 
+    /// Report the text of every slide to `visitor` in order, stopping as
+    /// soon as it returns [`ControlFlow::Break`].
+    ///
+    /// Each slide starts with [`TextEvent::SlideStart`], followed by its
+    /// paragraphs and, for .pptx, its tables. Slides of .pptx and .ppt files
+    /// are parsed one at a time as the walk reaches them, so no slide after
+    /// the point where the visitor stops is parsed.
+    ///
+    /// Returns whether the visitor stopped the walk.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    /// use litchi::common::walk::TextEvent;
+    /// use std::ops::ControlFlow;
+    ///
+    /// let pres = Presentation::open("deck.ppt")?;
+    /// // Print the text of the first slide only
+    /// pres.walk_text(|event| match event {
+    ///     TextEvent::SlideStart(index) if index > 0 => ControlFlow::Break(()),
+    ///     TextEvent::Text(text) => {
+    ///         print!("{text}");
+    ///         ControlFlow::Continue(())
+    ///     },
+    ///     TextEvent::ParagraphStart => {
+    ///         println!();
+    ///         ControlFlow::Continue(())
+    ///     },
+    ///     _ => ControlFlow::Continue(()),
+    /// })?;
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn walk_text<F>(&self, mut visitor: F) -> Result<ControlFlow<()>>
+    where
+        F: FnMut(TextEvent<'_>) -> ControlFlow<()>,
+    {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            PresentationImpl::Ppt(pres) => walk_ppt(pres, &mut visitor),
+            #[cfg(feature = "ooxml")]
+            PresentationImpl::Pptx(pres) => {
+                for (index, slide) in pres.slides().map_err(Error::from)?.iter().enumerate() {
+                    visit!(visitor, TextEvent::SlideStart(index));
+                    if slide.walk_text(&mut visitor)?.is_break() {
+                        return Ok(ControlFlow::Break(()));
+                    }
+                }
+                Ok(ControlFlow::Continue(()))
+            },
+            #[cfg(feature = "iwa")]
+            PresentationImpl::Keynote(_) => self.walk_slide_text(&mut visitor),
+            #[cfg(feature = "odf")]
+            PresentationImpl::Odp(_) => self.walk_slide_text(&mut visitor),
+        }
+    }
+
+    /// Report the text of slides that were read when the presentation was
+    /// opened, one paragraph per line.
+    #[cfg(any(feature = "odf", feature = "iwa"))]
+    fn walk_slide_text(
+        &self,
+        visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+    ) -> Result<ControlFlow<()>> {
+        for (index, slide) in self.slides()?.iter().enumerate() {
+            visit!(visitor, TextEvent::SlideStart(index));
+            if visit_lines(&slide.text()?, visitor).is_break() {
+                return Ok(ControlFlow::Break(()));
+            }
+        }
+        Ok(ControlFlow::Continue(()))
+    }
+
     /// Get the number of slides in the presentation.
     ///
     /// # Examples
@@ -309,20 +467,123 @@ impl Presentation {
     pub fn slide_count(&self) -> Result<usize> {
         match &self.inner {
             #[cfg(feature = "ole")]
-            PresentationImpl::Ppt(pres) => Ok(pres.slide_count()),
+            PresentationImpl::Ppt(pres) => Ok(pres.presentation()?.slide_count()),
             #[cfg(feature = "ooxml")]
             PresentationImpl::Pptx(pres) => pres.slide_count().map_err(Error::from),
             #[cfg(feature = "iwa")]
             PresentationImpl::Keynote(doc) => {
-                let slides = doc
-                    .slides()
-                    .map_err(|e| Error::ParseError(format!("Failed to get slides: {}", e)))?;
+                let slides = doc.slides().map_err(Error::from)?;
                 Ok(slides.len())
             },
             #[cfg(feature = "odf")]
-            PresentationImpl::Odp(doc) => doc
-                .slide_count()
-                .map_err(|e| Error::ParseError(format!("Failed to get ODP slide count: {}", e))),
+            PresentationImpl::Odp(doc) => doc.slide_count(),
+        }
+    }
+
+    /// Count the slides and words of the presentation.
+    ///
+    /// Words are counted in the same way for every format; see
+    /// [`PresentationStatistics`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.odp")?;
+    /// let stats = pres.statistics()?;
+    /// println!("Slides: {}", stats.slide_count());
+    /// println!("Words: {}", stats.word_count());
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn statistics(&self) -> Result<PresentationStatistics> {
+        let counts = TextCounts::from_walk(|visitor| self.walk_text(visitor))?;
+        let notes_word_count = self
+            .notes()?
+            .map(|notes| notes.iter().map(|text| count_words(text)).sum());
+        Ok(PresentationStatistics::new(
+            self.slide_count()?,
+            counts.words,
+            notes_word_count,
+            self.cached_metadata.as_ref(),
+        ))
+    }
+
+    /// Get the editing protection of the presentation.
+    ///
+    /// Reports the read-only recommendation and the "final" mark of the
+    /// document properties, a password to modify (.pptx) and the
+    /// `LoadReadonly` setting (.odp). Keynote files are never protected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("deck.pptx")?;
+    /// let protection = pres.protection()?;
+    /// if protection.is_protected() {
+    ///     println!("{:?}, password: {}", protection.kind, protection.has_password);
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn protection(&self) -> Result<Protection> {
+        let properties = self
+            .cached_metadata
+            .as_ref()
+            .map(Protection::from_metadata)
+            .unwrap_or_default();
+        let format = match &self.inner {
+            #[cfg(feature = "ole")]
+            PresentationImpl::Ppt(_) => Protection::default(),
+            #[cfg(feature = "ooxml")]
+            PresentationImpl::Pptx(pres) => {
+                if pres
+                    .protection()
+                    .map_err(Error::from)?
+                    .modify_password_protected
+                {
+                    Protection::with_password(crate::common::ProtectionKind::ReadOnly)
+                } else {
+                    Protection::default()
+                }
+            },
+            #[cfg(feature = "iwa")]
+            PresentationImpl::Keynote(_) => Protection::default(),
+            #[cfg(feature = "odf")]
+            PresentationImpl::Odp(doc) => doc.protection()?,
+        };
+        Ok(properties.or(format))
+    }
+
+    /// The speaker notes of each slide that has them, or `None` if the
+    /// format's notes are not read.
+    fn notes(&self) -> Result<Option<Vec<String>>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            PresentationImpl::Ppt(_) => Ok(None),
+            #[cfg(feature = "ooxml")]
+            PresentationImpl::Pptx(pres) => {
+                let mut notes = Vec::new();
+                for slide in pres.slides().map_err(Error::from)? {
+                    notes.extend(slide.notes().map_err(Error::from)?);
+                }
+                Ok(Some(notes))
+            },
+            #[cfg(feature = "iwa")]
+            PresentationImpl::Keynote(doc) => {
+                let slides = doc.slides().map_err(Error::from)?;
+                Ok(Some(
+                    slides.into_iter().filter_map(|slide| slide.notes).collect(),
+                ))
+            },
+            #[cfg(feature = "odf")]
+            PresentationImpl::Odp(doc) => {
+                let slides = doc.slides()?;
+                Ok(Some(
+                    slides.into_iter().filter_map(|slide| slide.notes).collect(),
+                ))
+            },
         }
     }
 
@@ -345,17 +606,34 @@ impl Presentation {
             PresentationImpl::Ppt(pres) => {
                 use super::types::PptSlideData;
                 // Extract slide data to avoid lifetime issues
-                let ppt_slides = pres.slides().map_err(Error::from)?;
+                let ppt_slides = pres.presentation()?.slides().map_err(Error::from)?;
                 ppt_slides
                     .iter()
                     .map(|s| {
                         let text = s.text().map_err(Error::from)?.to_string();
                         let slide_number = s.slide_number();
                         let shape_count = s.shape_count().unwrap_or(0);
+                        let title = s.title().unwrap_or_default();
+                        let body_text = s.body_text().unwrap_or_else(|_| text.clone());
+                        let text_without_tables =
+                            s.text_without_tables().unwrap_or_else(|_| text.clone());
+                        let tables = s
+                            .tables()
+                            .map(|tables| tables.into_iter().map(SlideTable::from_ppt).collect())
+                            .unwrap_or_default();
+                        let shapes = s
+                            .shapes()
+                            .map(|shapes| shapes.iter().map(SlideShape::from_ppt).collect())
+                            .unwrap_or_default();
                         Ok(Slide::Ppt(PptSlideData {
                             text,
                             slide_number,
                             shape_count,
+                            title,
+                            body_text,
+                            text_without_tables,
+                            tables,
+                            shapes,
                         }))
                     })
                     .collect()
@@ -370,30 +648,138 @@ impl Presentation {
                     .map(|s| {
                         let text = s.text().map_err(Error::from)?;
                         let name = s.name().ok();
-                        Ok(Slide::Pptx(PptxSlideData { text, name }))
+                        let title = s.title().unwrap_or_default();
+                        let body_text = s.body_text().unwrap_or_else(|_| text.clone());
+                        let text_without_tables =
+                            s.text_without_tables().unwrap_or_else(|_| text.clone());
+                        let tables = s
+                            .tables()
+                            .map_err(Error::from)
+                            .and_then(|tables| tables.iter().map(SlideTable::from_pptx).collect())
+                            .unwrap_or_default();
+                        let shapes = s
+                            .shapes()
+                            .map_err(Error::from)
+                            .and_then(|mut shapes| {
+                                shapes.iter_mut().map(SlideShape::from_pptx).collect()
+                            })
+                            .unwrap_or_default();
+                        let charts = s.charts().unwrap_or_default();
+                        let smartart = s.smartart().unwrap_or_default();
+                        Ok(Slide::Pptx(PptxSlideData {
+                            text,
+                            name,
+                            title,
+                            body_text,
+                            text_without_tables,
+                            tables,
+                            shapes,
+                            charts,
+                            smartart,
+                        }))
                     })
                     .collect()
             },
             #[cfg(feature = "iwa")]
             PresentationImpl::Keynote(doc) => {
-                let keynote_slides = doc
-                    .slides()
-                    .map_err(|e| Error::ParseError(format!("Failed to get slides: {}", e)))?;
+                let keynote_slides = doc.slides().map_err(Error::from)?;
                 Ok(keynote_slides.into_iter().map(Slide::Keynote).collect())
             },
             #[cfg(feature = "odf")]
             PresentationImpl::Odp(doc) => {
-                let odp_slides = doc
-                    .slides()
-                    .map_err(|e| Error::ParseError(format!("Failed to get ODP slides: {}", e)))?;
+                let odp_slides = doc.slides()?;
                 Ok(odp_slides.into_iter().map(Slide::Odp).collect())
             },
         }
     }
 
+    /// Find every occurrence of `pattern` in the slide text.
+    ///
+    /// The text of each shape is searched separately and matches report the
+    /// shape index. For formats without shape access the whole slide text is
+    /// searched instead. Shape text has no runs in the unified API, so every
+    /// match has a single segment for run 0.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the pattern is an invalid regular expression, or a regular
+    /// expression is requested without the `regex` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    /// use litchi::common::search::{MatchLocation, SearchOptions};
+    ///
+    /// let pres = Presentation::open("presentation.pptx")?;
+    /// let options = SearchOptions::new().with_case_insensitive(true);
+    /// for m in pres.find("roadmap", &options)? {
+    ///     if let MatchLocation::Slide { index, .. } = m.location() {
+    ///         println!("slide {}: {}", index + 1, m.snippet());
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn find(&self, pattern: &str, options: &SearchOptions) -> Result<Vec<TextMatch>> {
+        let matcher = Matcher::new(pattern, options)?;
+        let mut matches = Vec::new();
+
+        for (index, slide) in self.slides()?.iter().enumerate() {
+            let shapes = slide.shapes()?;
+            if shapes.is_empty() {
+                let location = MatchLocation::Slide { index, shape: None };
+                matches.extend(find_in_runs(&matcher, &[slide.text()?], location));
+                continue;
+            }
+            for (shape, slide_shape) in shapes.iter().enumerate() {
+                let location = MatchLocation::Slide {
+                    index,
+                    shape: Some(shape),
+                };
+                matches.extend(find_in_runs(&matcher, &[slide_shape.text()], location));
+            }
+        }
+
+        Ok(matches)
+    }
+
+    /// Get the slide size as (width, height).
+    ///
+    /// Read from the `p:sldSz` element of a .pptx and the DocumentAtom of a
+    /// .ppt. Returns `None` when the file does not store a size, and for
+    /// other formats.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.ppt")?;
+    /// if let Some((width, height)) = pres.slide_size()? {
+    ///     println!("Slides are {:.2} x {:.2} in", width.inches(), height.inches());
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn slide_size(&self) -> Result<Option<(Length, Length)>> {
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            PresentationImpl::Ppt(pres) => Ok(pres.presentation()?.slide_size()),
+            #[cfg(feature = "ooxml")]
+            PresentationImpl::Pptx(pres) => {
+                let width = pres.slide_width().map_err(Error::from)?;
+                let height = pres.slide_height().map_err(Error::from)?;
+                Ok(width
+                    .zip(height)
+                    .map(|(width, height)| (Length::from_emus(width), Length::from_emus(height))))
+            },
+            #[allow(unreachable_patterns)]
+            _ => Ok(None),
+        }
+    }
+
     /// Get the slide width in EMUs (English Metric Units).
     ///
-    /// Only available for .pptx format. Returns None for .ppt files.
+    /// Available for .ppt and .pptx files; see [`Self::slide_size`].
     ///
     /// # Examples
     ///
@@ -409,7 +795,9 @@ impl Presentation {
     pub fn slide_width(&self) -> Result<Option<i64>> {
         match &self.inner {
             #[cfg(feature = "ole")]
-            PresentationImpl::Ppt(_) => Ok(None),
+            PresentationImpl::Ppt(pres) => {
+                Ok(pres.presentation()?.slide_size().map(|size| size.0.emus()))
+            },
             #[cfg(feature = "ooxml")]
             PresentationImpl::Pptx(pres) => pres.slide_width().map_err(Error::from),
             #[cfg(feature = "iwa")]
@@ -421,7 +809,7 @@ impl Presentation {
 
     /// Get the slide height in EMUs (English Metric Units).
     ///
-    /// Only available for .pptx format. Returns None for .ppt files.
+    /// Available for .ppt and .pptx files; see [`Self::slide_size`].
     ///
     /// # Examples
     ///
@@ -437,7 +825,9 @@ impl Presentation {
     pub fn slide_height(&self) -> Result<Option<i64>> {
         match &self.inner {
             #[cfg(feature = "ole")]
-            PresentationImpl::Ppt(_) => Ok(None),
+            PresentationImpl::Ppt(pres) => {
+                Ok(pres.presentation()?.slide_size().map(|size| size.1.emus()))
+            },
             #[cfg(feature = "ooxml")]
             PresentationImpl::Pptx(pres) => pres.slide_height().map_err(Error::from),
             #[cfg(feature = "iwa")]
@@ -447,6 +837,71 @@ impl Presentation {
         }
     }
 
+    /// Check whether the presentation carries a VBA project (macros).
+    ///
+    /// This is true for macro-enabled .pptm files with a `vbaProject.bin`
+    /// part. The VBA project of legacy .ppt files, which is stored inside a
+    /// compressed OLE object, is not read.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.pptm")?;
+    /// if pres.has_macros() {
+    ///     println!("presentation contains macros");
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn has_macros(&self) -> bool {
+        match &self.inner {
+            #[cfg(feature = "ooxml")]
+            PresentationImpl::Pptx(pres) => pres.package().vba_project_part().is_some(),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Get the VBA project of the presentation, with the source code of its
+    /// modules.
+    ///
+    /// Returns `None` when the presentation has no macros (see
+    /// [`Self::has_macros`]).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the project is present but cannot be parsed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.pptm")?;
+    /// if let Some(project) = pres.vba_project()? {
+    ///     for module in project.modules() {
+    ///         println!("{}: {} bytes", module.name(), module.source().len());
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    #[cfg(feature = "ole")]
+    pub fn vba_project(&self) -> Result<Option<ole::vba::VbaProject>> {
+        let data = match &self.inner {
+            #[cfg(feature = "ooxml")]
+            PresentationImpl::Pptx(pres) => pres
+                .package()
+                .vba_project_part()
+                .map(|p| p.try_blob())
+                .transpose()?,
+            #[allow(unreachable_patterns)]
+            _ => None,
+        };
+        data.map(|data| ole::vba::VbaProject::parse(data).map_err(Error::from))
+            .transpose()
+    }
+
     /// Extract presentation metadata.
     ///
     /// Returns document properties like title, author, creation date, etc.
@@ -474,6 +929,269 @@ impl Presentation {
         Ok(self.cached_metadata.clone())
     }
 
+    /// Fast content extraction for markdown conversion (internal use).
+    ///
+    /// Like [`Self::extract_text_for_markdown`], but the text of each slide
+    /// leaves out table cells, which are returned as tables instead.
+    ///
+    /// SmartArt diagrams (.pptx only) are also returned separately, so that
+    /// they can be rendered as nested lists.
+    #[doc(hidden)]
+    pub fn extract_content_for_markdown(&self) -> Result<Vec<SlideContent>> {
+        // Only `ole` enabled: PresentationImpl can only be Ppt.
+        #[cfg(all(
+            feature = "ole",
+            not(any(feature = "ooxml", feature = "iwa", feature = "odf"))
+        ))]
+        {
+            let PresentationImpl::Ppt(pres) = &self.inner;
+            Self::ppt_content(pres.presentation()?)
+        }
+
+        #[cfg(not(all(
+            feature = "ole",
+            not(any(feature = "ooxml", feature = "iwa", feature = "odf"))
+        )))]
+        {
+            #[cfg(feature = "ole")]
+            if let PresentationImpl::Ppt(pres) = &self.inner {
+                return Self::ppt_content(pres.presentation()?);
+            }
+
+            let slides = self.slides()?;
+            Ok(slides
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, slide)| {
+                    Some(SlideContent {
+                        number: idx + 1,
+                        text: slide.text_without_tables().ok()?,
+                        tables: slide.tables().unwrap_or_default(),
+                        #[cfg(feature = "ooxml")]
+                        smartart: slide.smartart().unwrap_or_default(),
+                    })
+                })
+                .collect())
+        }
+    }
+
+    /// Convert the fast PPT content extraction into unified tables.
+    #[cfg(feature = "ole")]
+    fn ppt_content(pres: &ole::ppt::Presentation) -> Result<Vec<SlideContent>> {
+        let content = pres.extract_content_fast().map_err(Error::from)?;
+        Ok(content
+            .into_iter()
+            .map(|(number, text, tables)| SlideContent {
+                number,
+                text,
+                tables: tables.iter().map(SlideTable::from_ppt).collect(),
+                #[cfg(feature = "ooxml")]
+                smartart: Vec::new(),
+            })
+            .collect())
+    }
+
+    /// Content extraction for HTML export (internal use).
+    ///
+    /// Reads the shapes of the first `max_slides` slides (all slides if
+    /// `None`) with their geometry, styled text, pictures and tables, and
+    /// the speaker notes (.pptx only). For formats without shape geometry,
+    /// the slide text is returned as a single shape without a position.
+    #[cfg(feature = "html")]
+    #[doc(hidden)]
+    pub fn extract_content_for_html(
+        &self,
+        max_slides: Option<usize>,
+    ) -> Result<Vec<PositionedSlide>> {
+        let limit = max_slides.unwrap_or(usize::MAX);
+        match &self.inner {
+            #[cfg(feature = "ole")]
+            PresentationImpl::Ppt(pres) => Self::ppt_positioned(pres.presentation()?, limit),
+            #[cfg(feature = "ooxml")]
+            PresentationImpl::Pptx(pres) => Self::pptx_positioned(pres, limit),
+            #[allow(unreachable_patterns)]
+            _ => {
+                let slides = self.slides()?;
+                slides
+                    .iter()
+                    .take(limit)
+                    .enumerate()
+                    .map(|(idx, slide)| {
+                        Ok(PositionedSlide {
+                            number: idx + 1,
+                            shapes: vec![PositionedShape {
+                                paragraphs: plain_paragraphs(&slide.text()?, &StyledRun::default()),
+                                ..Default::default()
+                            }],
+                            notes: None,
+                        })
+                    })
+                    .collect()
+            },
+        }
+    }
+
+    /// Read the positioned shapes of PPTX slides.
+    #[cfg(all(feature = "html", feature = "ooxml"))]
+    fn pptx_positioned(
+        pres: &ooxml::pptx::Presentation,
+        limit: usize,
+    ) -> Result<Vec<PositionedSlide>> {
+        use crate::ooxml::docx::format::ImageFormat;
+        use crate::ooxml::drawings::color::ColorScheme;
+        use crate::ooxml::pptx::shapes::{Picture, ShapeType, TextFrame};
+
+        // Theme colors are resolved against the first theme
+        let scheme = pres
+            .get_themes()
+            .ok()
+            .and_then(|themes| themes.first().map(|theme| theme.color_scheme()))
+            .unwrap_or_default();
+        let styled = |run: ooxml::pptx::shapes::TextRun, scheme: &ColorScheme| StyledRun {
+            text: run.text,
+            font_size: run.font_size,
+            color: run.color.and_then(|color| color.resolve(scheme)),
+            bold: run.bold.unwrap_or(false),
+            italic: run.italic.unwrap_or(false),
+            underline: run.underline.unwrap_or(false),
+        };
+
+        let slides = pres.slides().map_err(Error::from)?;
+        slides
+            .iter()
+            .take(limit)
+            .enumerate()
+            .map(|(idx, slide)| {
+                let mut shapes = Vec::new();
+                for mut shape in slide.shapes().map_err(Error::from)? {
+                    let mut positioned = PositionedShape {
+                        bounds: slide.shape_bounds(&mut shape).map_err(Error::from)?,
+                        rotation: shape.rotation().map_err(Error::from)?,
+                        flip_horizontal: shape.flip_horizontal().map_err(Error::from)?,
+                        flip_vertical: shape.flip_vertical().map_err(Error::from)?,
+                        fill: shape.fill_color(&scheme).map_err(Error::from)?,
+                        ..Default::default()
+                    };
+                    match shape.shape_type() {
+                        ShapeType::Shape => {
+                            for paragraph in TextFrame::from_xml(shape.xml_bytes())
+                                .and_then(|frame| frame.paragraphs())
+                                .map_err(Error::from)?
+                            {
+                                let runs = paragraph.runs().map_err(Error::from)?;
+                                positioned.paragraphs.push(
+                                    runs.into_iter().map(|run| styled(run, &scheme)).collect(),
+                                );
+                            }
+                        },
+                        ShapeType::Picture => {
+                            let picture = Picture::new(shape.xml_bytes().to_vec());
+                            if let Ok(r_id) = picture.image_r_id()
+                                && let Some((_, data)) = slide.image(&r_id).map_err(Error::from)?
+                                && let Some(format) = ImageFormat::detect_from_bytes(data)
+                            {
+                                positioned.image = Some((data.to_vec(), format.extension()));
+                            }
+                        },
+                        ShapeType::GraphicFrame => {
+                            if let Some(table) = shape.table().map_err(Error::from)? {
+                                positioned.table = Some(SlideTable::from_pptx(&table)?);
+                            }
+                        },
+                        _ => {},
+                    }
+                    shapes.push(positioned);
+                }
+
+                Ok(PositionedSlide {
+                    number: idx + 1,
+                    shapes,
+                    notes: slide.notes().map_err(Error::from)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Read the positioned shapes of PPT slides.
+    ///
+    /// Text formatting is taken from the shape, so all runs of a text box
+    /// share it. Pictures need the `imgconv` feature to be read.
+    #[cfg(all(feature = "html", feature = "ole"))]
+    fn ppt_positioned(pres: &ole::ppt::Presentation, limit: usize) -> Result<Vec<PositionedSlide>> {
+        use crate::common::RGBColor;
+        use crate::ole::ppt::shapes::ShapeEnum;
+
+        // The Pictures stream is read once and indexed by BLIP id
+        #[cfg(feature = "imgconv")]
+        let images = pres.extract_all_images().unwrap_or_default();
+
+        let slides = pres.slides().map_err(Error::from)?;
+        slides
+            .iter()
+            .take(limit)
+            .map(|slide| {
+                let mut shapes = Vec::new();
+                for shape in slide.shapes().map_err(Error::from)? {
+                    let mut positioned = PositionedShape {
+                        bounds: shape.bounds(),
+                        rotation: shape.rotation(),
+                        flip_horizontal: shape.flip_horizontal(),
+                        flip_vertical: shape.flip_vertical(),
+                        ..Default::default()
+                    };
+                    match shape {
+                        ShapeEnum::Table(table) => {
+                            positioned.table = Some(SlideTable::from_ppt(table));
+                        },
+                        #[cfg(feature = "imgconv")]
+                        ShapeEnum::Picture(picture) => {
+                            let image = match picture
+                                .blip_id()
+                                .and_then(|id| images.get(id.checked_sub(1)? as usize))
+                            {
+                                Some(image) => Some(image.clone()),
+                                None => picture.extract_image(pres).ok().flatten(),
+                            };
+                            positioned.image = image.and_then(|image| match image.extension() {
+                                "emf" | "wmf" | "pict" => {
+                                    image.to_png(None, None).ok().map(|png| (png, "png"))
+                                },
+                                extension => image
+                                    .decompressed_data()
+                                    .ok()
+                                    .map(|data| (data.into_owned(), extension)),
+                            });
+                        },
+                        ShapeEnum::TextBox(text_box) => {
+                            let style = StyledRun {
+                                font_size: text_box.font_size().map(f64::from),
+                                color: text_box.font_color().map(|rgb| {
+                                    RGBColor::new((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+                                }),
+                                bold: text_box.bold(),
+                                italic: text_box.italic(),
+                                underline: text_box.underline(),
+                                ..Default::default()
+                            };
+                            positioned.paragraphs = plain_paragraphs(text_box.text(), &style);
+                        },
+                        _ => {
+                            let text = shape.text().unwrap_or_default();
+                            positioned.paragraphs = plain_paragraphs(&text, &StyledRun::default());
+                        },
+                    }
+                    shapes.push(positioned);
+                }
+
+                Ok(PositionedSlide {
+                    number: slide.slide_number(),
+                    shapes,
+                    notes: None,
+                })
+            })
+            .collect()
+    }
+
     /// Fast text extraction for markdown conversion (internal use).
     ///
     /// This method is optimized for PPT files by skipping shape parsing.
@@ -498,7 +1216,9 @@ impl Presentation {
         ))]
         {
             let PresentationImpl::Ppt(pres) = &self.inner;
-            pres.extract_text_fast().map_err(Error::from)
+            pres.presentation()?
+                .extract_text_fast()
+                .map_err(Error::from)
         }
 
         // When multiple presentation formats are compiled in, prefer the fast
@@ -511,7 +1231,10 @@ impl Presentation {
         {
             #[cfg(feature = "ole")]
             if let PresentationImpl::Ppt(pres) = &self.inner {
-                return pres.extract_text_fast().map_err(Error::from);
+                return pres
+                    .presentation()?
+                    .extract_text_fast()
+                    .map_err(Error::from);
             }
 
             // For other formats, extract from slides (slower but works)
@@ -527,6 +1250,46 @@ impl Presentation {
     }
 }
 
+/// Walk the slides of a .ppt file, reading them from the package one at a
+/// time so that nothing after the slide where the visitor stops is read.
+#[cfg(feature = "ole")]
+fn walk_ppt<R: std::io::Read + std::io::Seek>(
+    pres: &PptFile<R>,
+    visitor: &mut dyn FnMut(TextEvent<'_>) -> ControlFlow<()>,
+) -> Result<ControlFlow<()>> {
+    use crate::ole::ppt::slide::reader::SlideTextReader;
+
+    let reader = SlideTextReader::open(pres.package().ole_file())?;
+    let Some(mut reader) = reader else {
+        return pres.presentation()?.walk_text(visitor).map_err(Error::from);
+    };
+    loop {
+        // The package is only locked while reading, so the visitor may use the presentation
+        let slide = reader.next_slide(pres.package().ole_file())?;
+        let Some((index, text)) = slide else {
+            return Ok(ControlFlow::Continue(()));
+        };
+        visit!(visitor, TextEvent::SlideStart(index));
+        if visit_lines(&text, visitor).is_break() {
+            return Ok(ControlFlow::Break(()));
+        }
+    }
+}
+
+/// Split plain text into paragraphs of one run each, all with the
+/// formatting of `style`. Vertical tabs are line breaks within a paragraph.
+#[cfg(feature = "html")]
+fn plain_paragraphs(text: &str, style: &StyledRun) -> Vec<Vec<StyledRun>> {
+    text.split(['\r', '\n'])
+        .map(|line| {
+            vec![StyledRun {
+                text: line.replace('\u{b}', "\n"),
+                ..style.clone()
+            }]
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -536,6 +1299,260 @@ mod tests {
         PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data")
     }
 
+    /// Debug strings of the events `pres` reports up to the start of slide
+    /// `stop_at`, where the walk is stopped.
+    fn walk_until_slide(
+        pres: &Presentation,
+        stop_at: usize,
+    ) -> Result<(ControlFlow<()>, Vec<String>)> {
+        let mut events = Vec::new();
+        let flow = pres.walk_text(|event| {
+            if event == TextEvent::SlideStart(stop_at) {
+                return ControlFlow::Break(());
+            }
+            events.push(format!("{event:?}"));
+            ControlFlow::Continue(())
+        })?;
+        Ok((flow, events))
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_presentation_walk_text_pptx() {
+        let pres =
+            Presentation::open(test_data_path().join("ooxml/pptx/backgrounds.pptx")).unwrap();
+
+        let (flow, events) = walk_until_slide(&pres, usize::MAX).unwrap();
+        assert!(flow.is_continue());
+        let slides: Vec<_> = events
+            .iter()
+            .filter(|e| e.starts_with("SlideStart"))
+            .collect();
+        assert_eq!(
+            slides,
+            [
+                "SlideStart(0)",
+                "SlideStart(1)",
+                "SlideStart(2)",
+                "SlideStart(3)"
+            ]
+        );
+
+        let (flow, events) = walk_until_slide(&pres, 1).unwrap();
+        assert!(flow.is_break());
+        assert_eq!(
+            events[..3],
+            ["SlideStart(0)", "ParagraphStart", "Text(\"Solid Fill\")"]
+        );
+        assert!(!events.iter().any(|e| e.contains("Gradient")));
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_presentation_walk_text_stops_before_later_slides() {
+        use std::io::{Cursor, Read, Write};
+
+        // Replace every slide but the first with malformed XML: a walk that
+        // stops after the first slide must never parse them
+        let source = std::fs::read(test_data_path().join("ooxml/pptx/backgrounds.pptx")).unwrap();
+        let mut archive = zip::ZipArchive::new(Cursor::new(source)).unwrap();
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).unwrap();
+            let name = entry.name().to_string();
+            let mut data = Vec::new();
+            entry.read_to_end(&mut data).unwrap();
+            writer
+                .start_file(name.as_str(), zip::write::SimpleFileOptions::default())
+                .unwrap();
+            if name.starts_with("ppt/slides/slide") && name != "ppt/slides/slide1.xml" {
+                data = b"<p:sld><p:cSld".to_vec();
+            }
+            writer.write_all(&data).unwrap();
+        }
+        let bytes = writer.finish().unwrap().into_inner();
+        let pres = Presentation::from_bytes(bytes).unwrap();
+
+        let (flow, events) = walk_until_slide(&pres, 1).unwrap();
+        assert!(flow.is_break());
+        assert!(events.contains(&"Text(\"Solid Fill\")".to_string()));
+        assert!(walk_until_slide(&pres, usize::MAX).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_presentation_statistics_pptx() {
+        let path = test_data_path().join("ooxml/pptx/smartart-snake-rows.pptx");
+        let stats = Presentation::open(path).unwrap().statistics().unwrap();
+        assert_eq!(stats.slide_count(), 1);
+        assert_eq!(stats.word_count(), 24);
+        assert_eq!(stats.notes_word_count(), Some(2));
+        // PowerPoint counts slide and notes words together
+        assert_eq!(stats.reported_word_count(), Some(26));
+
+        let path = test_data_path().join("ooxml/pptx/sample.pptx");
+        let stats = Presentation::open(path).unwrap().statistics().unwrap();
+        assert_eq!(stats.slide_count(), 2);
+        let words = stats.word_count() + stats.notes_word_count().unwrap();
+        let reported = stats.reported_word_count().unwrap() as usize;
+        assert!(
+            words.abs_diff(reported) <= reported / 10,
+            "{words} vs {reported}"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_presentation_statistics_ppt() {
+        let path = test_data_path().join("ole/ppt/text_shapes.ppt");
+        let stats = Presentation::open(path).unwrap().statistics().unwrap();
+        assert_eq!(stats.slide_count(), 1);
+        assert_eq!(stats.word_count(), 9);
+        assert_eq!(stats.reported_word_count(), Some(9));
+        assert_eq!(stats.notes_word_count(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "odf")]
+    fn test_presentation_statistics_odp() {
+        let mut builder = crate::odf::PresentationBuilder::new();
+        builder
+            .add_slide_with_title("Menu", "Fish & chips")
+            .unwrap();
+        builder.add_slide("日本語").unwrap();
+        builder.set_notes(1, "Mention the price").unwrap();
+        let pres = Presentation::from_bytes(builder.build().unwrap()).unwrap();
+
+        let stats = pres.statistics().unwrap();
+        assert_eq!(stats.slide_count(), 2);
+        assert_eq!(stats.word_count(), 6);
+        assert_eq!(stats.notes_word_count(), Some(3));
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_presentation_walk_text_ppt() {
+        let pres = Presentation::open(test_data_path().join("ole/ppt/SampleShow.ppt")).unwrap();
+
+        let (flow, all) = walk_until_slide(&pres, usize::MAX).unwrap();
+        assert!(flow.is_continue());
+        let slides = all.iter().filter(|e| e.starts_with("SlideStart")).count();
+        assert_eq!(slides, pres.slide_count().unwrap());
+        assert!(slides > 1);
+
+        let (flow, first) = walk_until_slide(&pres, 1).unwrap();
+        assert!(flow.is_break());
+        assert_eq!(first[0], "SlideStart(0)");
+        assert!(first.len() < all.len());
+        assert_eq!(first[..], all[..first.len()]);
+    }
+
+    /// Open a .ppt file over a reader that logs the byte ranges read after opening.
+    #[cfg(feature = "ole")]
+    fn counted_ppt(
+        bytes: &[u8],
+    ) -> (
+        PptFile<ole::CountingReader<std::io::Cursor<Vec<u8>>>>,
+        ole::ReadLog,
+    ) {
+        let reader = ole::CountingReader::new(std::io::Cursor::new(bytes.to_vec()));
+        let reads = reader.reads();
+        let ole_file = ole::OleFile::open(reader).unwrap();
+        let file = PptFile::new(ole::ppt::Package::from_ole_file(ole_file).unwrap());
+        reads.borrow_mut().clear();
+        (file, reads)
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_presentation_walk_text_ppt_stops_reading_after_break() {
+        use crate::ole::ppt::slide::reader::SlideTextReader;
+
+        let bytes = std::fs::read(test_data_path().join("ole/ppt/SampleShow.ppt")).unwrap();
+
+        // Bytes read for each slide, reading the slides one by one
+        let (file, reads) = counted_ppt(&bytes);
+        let mut reader = SlideTextReader::open(file.package().ole_file())
+            .unwrap()
+            .unwrap();
+        let mut slide_reads = Vec::new();
+        loop {
+            reads.borrow_mut().clear();
+            if reader
+                .next_slide(file.package().ole_file())
+                .unwrap()
+                .is_none()
+            {
+                break;
+            }
+            slide_reads.push(reads.borrow().clone());
+        }
+        assert!(slide_reads.len() > 1);
+        let later = slide_reads[1..].concat();
+        assert!(!later.is_empty());
+        let reads_later_slides = |reads: &[std::ops::Range<u64>]| {
+            reads.iter().any(|read| {
+                later
+                    .iter()
+                    .any(|l| read.start < l.end && l.start < read.end)
+            })
+        };
+
+        // Stopping at the start of the first slide leaves the slides after it unread
+        let (file, reads) = counted_ppt(&bytes);
+        let flow = walk_ppt(&file, &mut |event| {
+            if event == TextEvent::SlideStart(0) {
+                return ControlFlow::Break(());
+            }
+            ControlFlow::Continue(())
+        })
+        .unwrap();
+        assert!(flow.is_break());
+        assert!(!reads_later_slides(&reads.borrow()));
+
+        // A full walk reads them
+        let (file, reads) = counted_ppt(&bytes);
+        let flow = walk_ppt(&file, &mut |_| ControlFlow::Continue(())).unwrap();
+        assert!(flow.is_continue());
+        assert!(reads_later_slides(&reads.borrow()));
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_presentation_walk_text_ppt_matches_parsed() {
+        let mut paths: Vec<_> = std::fs::read_dir(test_data_path().join("ole/ppt"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        paths.sort();
+        for path in paths {
+            let pres = Presentation::open(&path).unwrap();
+            let PresentationImpl::Ppt(file) = &pres.inner else {
+                panic!("{} is not a .ppt file", path.display());
+            };
+
+            let mut streamed = Vec::new();
+            let flow = walk_ppt(file, &mut |event| {
+                streamed.push(format!("{event:?}"));
+                ControlFlow::Continue(())
+            })
+            .unwrap();
+            assert!(flow.is_continue());
+
+            let mut parsed = Vec::new();
+            let flow = file
+                .presentation()
+                .unwrap()
+                .walk_text(&mut |event| {
+                    parsed.push(format!("{event:?}"));
+                    ControlFlow::Continue(())
+                })
+                .unwrap();
+            assert!(flow.is_continue());
+            assert_eq!(streamed, parsed, "{}", path.display());
+        }
+    }
+
     #[test]
     #[cfg(all(feature = "ooxml", feature = "ole"))]
     fn test_presentation_open_pptx() {
@@ -684,6 +1701,30 @@ mod tests {
         assert!(result.is_err(), "Expected error for invalid data");
     }
 
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_presentation_metadata_custom_properties() {
+        use crate::common::CustomPropertyValue;
+
+        let path = test_data_path().join("ooxml/pptx/shape-soft-edges.pptx");
+        let pres = Presentation::open(&path).expect("Failed to open PPTX file");
+        let metadata = pres
+            .metadata()
+            .expect("Failed to read metadata")
+            .expect("Expected metadata");
+
+        assert_eq!(metadata.title.as_deref(), Some("PowerPoint Presentation"));
+        assert_eq!(metadata.author.as_deref(), Some("Mike Kaganski"));
+        assert_eq!(
+            metadata.custom_properties.get("PresentationFormat"),
+            Some(&CustomPropertyValue::String("Widescreen".to_string()))
+        );
+        assert_eq!(
+            metadata.custom_properties.get("Slides"),
+            Some(&CustomPropertyValue::Integer(1))
+        );
+    }
+
     #[test]
     #[cfg(all(feature = "ooxml", feature = "ole"))]
     fn test_presentation_charts_pptx() {
@@ -764,4 +1805,115 @@ mod tests {
             .expect("Failed to extract text");
         assert!(!slides_text.is_empty(), "Expected text extraction results");
     }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_presentation_smartart_markdown() {
+        use crate::markdown::ToMarkdown;
+
+        let path = test_data_path().join("ooxml/pptx/smartart-children.pptx");
+        let pres = Presentation::open(&path).expect("Failed to open PPTX");
+        let slides = pres.slides().unwrap();
+        let diagrams = slides[0].smartart().unwrap();
+        assert_eq!(diagrams.len(), 1);
+        assert_eq!(diagrams[0].nodes()[1].children[1].text, "z");
+        assert_eq!(slides[0].text().unwrap(), "a\nb\nc\nx\ny\nz");
+
+        // SmartArt nodes become a nested list instead of plain lines
+        let expected = "- a\n  - b\n  - c\n- x\n  - y\n  - z\n";
+        assert_eq!(
+            pres.to_markdown().unwrap(),
+            format!("# Slide 1\n\n{}\n", expected)
+        );
+        assert_eq!(slides[0].to_markdown().unwrap(), format!("{}\n", expected));
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_presentation_from_corrupted_ppt_bytes() {
+        // Truncated and corrupted files must fail with an error, not panic
+        let data = std::fs::read(test_data_path().join("ole/ppt/SampleShow.ppt")).unwrap();
+        for len in (0..data.len()).step_by(1024) {
+            if let Ok(pres) = Presentation::from_bytes(data[..len].to_vec()) {
+                let _ = pres.text();
+            }
+        }
+
+        let mut mutator = crate::ole::ByteMutator::new(0x9E37_79B9_7F4A_7C15);
+        for _ in 0..200 {
+            let mut corrupted = data.clone();
+            mutator.mutate(&mut corrupted, 16);
+            if let Ok(pres) = Presentation::from_bytes(corrupted) {
+                let _ = pres.text();
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_presentation_slide_size() {
+        // A 16:9 .ppt, so the size cannot come from a 4:3 default
+        let mut writer = crate::ole::ppt::PptWriter::new_widescreen();
+        writer.add_slide().unwrap();
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        writer.write_to(&mut bytes).unwrap();
+        let pres = Presentation::from_bytes(bytes.into_inner()).unwrap();
+        let (width, height) = pres.slide_size().unwrap().unwrap();
+        assert_eq!(width, Length::from_inches(10.0));
+        assert_eq!(height, Length::from_inches(5.625));
+        assert_eq!(pres.slide_width().unwrap(), Some(width.emus()));
+        assert_eq!(pres.slide_height().unwrap(), Some(height.emus()));
+
+        let pres = Presentation::open(test_data_path().join("ooxml/pptx/sample.pptx")).unwrap();
+        let (width, height) = pres.slide_size().unwrap().unwrap();
+        assert_eq!(Some(width.emus()), pres.slide_width().unwrap());
+        assert_eq!(Some(height.emus()), pres.slide_height().unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_presentation_find_ppt() {
+        let path = test_data_path().join("ole/ppt/NestedGroups.ppt");
+        let pres = Presentation::open(&path).unwrap();
+
+        let options = SearchOptions::new().with_case_insensitive(true);
+        let matches = pres.find("level", &options).unwrap();
+        let found: Vec<_> = matches.iter().map(|m| (m.location(), m.range())).collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    MatchLocation::Slide {
+                        index: 0,
+                        shape: Some(0)
+                    },
+                    4..9
+                ),
+                (
+                    MatchLocation::Slide {
+                        index: 0,
+                        shape: Some(1)
+                    },
+                    0..5
+                ),
+                (
+                    MatchLocation::Slide {
+                        index: 0,
+                        shape: Some(1)
+                    },
+                    15..20
+                ),
+                (
+                    MatchLocation::Slide {
+                        index: 0,
+                        shape: Some(1)
+                    },
+                    30..35
+                ),
+            ]
+        );
+        assert_eq!(matches[1].text(), "Level");
+        assert_eq!(matches[0].snippet(), "Top level text");
+        assert_eq!(pres.find("level", &SearchOptions::new()).unwrap().len(), 1);
+    }
 }
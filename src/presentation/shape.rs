@@ -0,0 +1,134 @@
+//! Format-independent shapes read from presentation slides.
+use crate::common::{Rect, ShapeType};
+
+/// A shape on a slide with its geometry.
+///
+/// Bounds are the unrotated frame of the shape in EMUs. To place a rotated
+/// or flipped shape, turn the frame around its centre by
+/// [`rotation`](Self::rotation) and mirror it by the flips.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Presentation;
+///
+/// let pres = Presentation::open("presentation.pptx")?;
+/// for slide in pres.slides()? {
+///     for shape in slide.shapes()? {
+///         if let Some(bounds) = shape.bounds() {
+///             println!(
+///                 "{} at ({:.1}pt, {:.1}pt), rotated {} degrees",
+///                 shape.shape_type(),
+///                 bounds.x().points(),
+///                 bounds.y().points(),
+///                 shape.rotation()
+///             );
+///         }
+///     }
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlideShape {
+    shape_type: ShapeType,
+    text: String,
+    bounds: Option<Rect>,
+    rotation: f64,
+    flip_horizontal: bool,
+    flip_vertical: bool,
+}
+
+impl SlideShape {
+    /// Build a shape from a PPTX shape.
+    #[cfg(feature = "ooxml")]
+    pub(crate) fn from_pptx(
+        shape: &mut crate::ooxml::pptx::shapes::base::BaseShape,
+    ) -> crate::common::Result<Self> {
+        use crate::ooxml::pptx::shapes::base::ShapeType as PptxShapeType;
+
+        let shape_type = match shape.shape_type() {
+            PptxShapeType::Shape if shape.is_placeholder() => ShapeType::Placeholder,
+            PptxShapeType::Shape => ShapeType::AutoShape,
+            PptxShapeType::Picture => ShapeType::Picture,
+            PptxShapeType::GraphicFrame if shape.has_table() => ShapeType::Table,
+            PptxShapeType::GraphicFrame => ShapeType::GraphicFrame,
+            PptxShapeType::GroupShape => ShapeType::Group,
+            PptxShapeType::Connector => ShapeType::Connector,
+            PptxShapeType::Unknown => ShapeType::Unknown,
+        };
+
+        Ok(Self {
+            shape_type,
+            text: shape.text()?.unwrap_or_default(),
+            bounds: shape.bounds()?,
+            rotation: shape.rotation()?,
+            flip_horizontal: shape.flip_horizontal()?,
+            flip_vertical: shape.flip_vertical()?,
+        })
+    }
+
+    /// Build a shape from a PPT shape.
+    #[cfg(feature = "ole")]
+    pub(crate) fn from_ppt(shape: &crate::ole::ppt::shapes::ShapeEnum<'static>) -> Self {
+        use crate::ole::ppt::shapes::ShapeType as PptShapeType;
+
+        let shape_type = match shape.shape_type() {
+            PptShapeType::TextBox => ShapeType::TextBox,
+            PptShapeType::Placeholder => ShapeType::Placeholder,
+            PptShapeType::AutoShape => ShapeType::AutoShape,
+            PptShapeType::Picture => ShapeType::Picture,
+            PptShapeType::Group => ShapeType::Group,
+            PptShapeType::Line => ShapeType::Line,
+            PptShapeType::Connector => ShapeType::Connector,
+            PptShapeType::Table => ShapeType::Table,
+            PptShapeType::Object => ShapeType::GraphicFrame,
+            PptShapeType::Unknown(_) => ShapeType::Unknown,
+        };
+
+        Self {
+            shape_type,
+            text: shape.text().unwrap_or_default(),
+            bounds: shape.bounds(),
+            rotation: shape.rotation(),
+            flip_horizontal: shape.flip_horizontal(),
+            flip_vertical: shape.flip_vertical(),
+        }
+    }
+
+    /// Get the type of the shape.
+    #[inline]
+    pub fn shape_type(&self) -> ShapeType {
+        self.shape_type
+    }
+
+    /// Get the text of the shape, empty if it has none.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the bounding box of the shape, if it has its own position.
+    #[inline]
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    /// Get the clockwise rotation in degrees.
+    #[inline]
+    pub fn rotation(&self) -> f64 {
+        self.rotation
+    }
+
+    /// Check whether the shape is flipped horizontally.
+    #[inline]
+    pub fn flip_horizontal(&self) -> bool {
+        self.flip_horizontal
+    }
+
+    /// Check whether the shape is flipped vertically.
+    #[inline]
+    pub fn flip_vertical(&self) -> bool {
+        self.flip_vertical
+    }
+}
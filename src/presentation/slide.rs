@@ -1,5 +1,7 @@
 //! Slide implementation for PowerPoint presentations.
 
+use super::shape::SlideShape;
+use super::table::SlideTable;
 use super::types::{PptSlideData, PptxSlideData};
 use crate::common::Result;
 
@@ -49,9 +51,7 @@ impl Slide {
                 Ok(text)
             },
             #[cfg(feature = "odf")]
-            Slide::Odp(slide) => slide.text().map(|s| s.to_string()).map_err(|e| {
-                crate::common::Error::ParseError(format!("Failed to get ODP slide text: {}", e))
-            }),
+            Slide::Odp(slide) => slide.text().map(|s| s.to_string()),
         }
     }
 
@@ -138,6 +138,207 @@ impl Slide {
             Slide::Odp(_slide) => Ok(None), // ODP slides don't have names in the current API
         }
     }
+
+    /// Get the title of the slide.
+    ///
+    /// For .pptx files this is the text of the `title` or `ctrTitle`
+    /// placeholder; for .ppt files it is the text marked as a title or center
+    /// title by its TextHeaderAtom. Returns None if the slide has no title or
+    /// for .odp files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.ppt")?;
+    /// for slide in pres.slides()? {
+    ///     if let Some(title) = slide.title()? {
+    ///         println!("Title: {}", title);
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn title(&self) -> Result<Option<String>> {
+        match self {
+            Slide::Ppt(data) => Ok(data.title.clone()),
+            Slide::Pptx(data) => Ok(data.title.clone()),
+            #[cfg(feature = "iwa")]
+            Slide::Keynote(slide) => Ok(slide.title.clone()),
+            #[cfg(feature = "odf")]
+            Slide::Odp(_) => Ok(None), // Title placeholders not currently exposed for ODP
+        }
+    }
+
+    /// Get the text content of the slide without its title.
+    ///
+    /// Together with [`Slide::title`], this lets callers process the title and
+    /// the rest of the slide separately without duplicating the title text.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.pptx")?;
+    /// for slide in pres.slides()? {
+    ///     let title = slide.title()?.unwrap_or_default();
+    ///     println!("{}: {}", title, slide.body_text()?);
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn body_text(&self) -> Result<String> {
+        match self {
+            Slide::Ppt(data) => Ok(data.body_text.clone()),
+            Slide::Pptx(data) => Ok(data.body_text.clone()),
+            #[cfg(feature = "iwa")]
+            Slide::Keynote(slide) => Ok(slide.text_content.join("\n")),
+            #[cfg(feature = "odf")]
+            Slide::Odp(_) => self.text(),
+        }
+    }
+
+    /// Get the tables on the slide.
+    ///
+    /// Tables are read from `a:tbl` graphic frames in .pptx files and from
+    /// table groups in .ppt files. Returns an empty list for .key and .odp
+    /// files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.pptx")?;
+    /// for slide in pres.slides()? {
+    ///     for table in slide.tables()? {
+    ///         println!("{}x{} table", table.row_count(), table.column_count());
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn tables(&self) -> Result<Vec<SlideTable>> {
+        match self {
+            Slide::Ppt(data) => Ok(data.tables.clone()),
+            Slide::Pptx(data) => Ok(data.tables.clone()),
+            #[cfg(feature = "iwa")]
+            Slide::Keynote(_) => Ok(Vec::new()), // Tables not currently exposed for Keynote
+            #[cfg(feature = "odf")]
+            Slide::Odp(_) => Ok(Vec::new()), // Tables not currently exposed for ODP
+        }
+    }
+
+    /// Get the shapes on the slide with their bounds, rotation and flips.
+    ///
+    /// Bounds are read from the `a:xfrm` transforms in .pptx files and from
+    /// the Escher anchors in .ppt files, both in EMUs. Shapes inside groups
+    /// are listed after their group in .ppt files. Returns an empty list for
+    /// .key and .odp files.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.ppt")?;
+    /// for slide in pres.slides()? {
+    ///     for shape in slide.shapes()? {
+    ///         println!("{}: {:?}", shape.shape_type(), shape.bounds());
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    pub fn shapes(&self) -> Result<Vec<SlideShape>> {
+        match self {
+            Slide::Ppt(data) => Ok(data.shapes.clone()),
+            Slide::Pptx(data) => Ok(data.shapes.clone()),
+            #[cfg(feature = "iwa")]
+            Slide::Keynote(_) => Ok(Vec::new()), // Shape geometry not currently exposed for Keynote
+            #[cfg(feature = "odf")]
+            Slide::Odp(_) => Ok(Vec::new()), // Shape geometry not currently exposed for ODP
+        }
+    }
+
+    /// Get the charts on the slide.
+    ///
+    /// Only available for .pptx format. Returns an empty list for other formats.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.pptx")?;
+    /// for slide in pres.slides()? {
+    ///     for chart in slide.charts()? {
+    ///         println!("Chart: {:?}", chart.title);
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    #[cfg(feature = "ooxml")]
+    pub fn charts(&self) -> Result<Vec<crate::ooxml::charts::Chart>> {
+        match self {
+            Slide::Pptx(data) => Ok(data.charts.clone()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the SmartArt diagrams on the slide.
+    ///
+    /// Only available for .pptx format. Returns an empty list for other formats.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::Presentation;
+    ///
+    /// let pres = Presentation::open("presentation.pptx")?;
+    /// for slide in pres.slides()? {
+    ///     for smartart in slide.smartart()? {
+    ///         println!("{}", smartart.text());
+    ///     }
+    /// }
+    /// # Ok::<(), litchi::common::Error>(())
+    /// ```
+    #[cfg(feature = "ooxml")]
+    pub fn smartart(&self) -> Result<Vec<crate::ooxml::pptx::SmartArt>> {
+        match self {
+            Slide::Pptx(data) => Ok(data.smartart.clone()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Get the text of the slide without the text of its tables.
+    pub(crate) fn text_without_tables(&self) -> Result<String> {
+        match self {
+            Slide::Ppt(data) => Ok(data.text_without_tables.clone()),
+            Slide::Pptx(data) => Ok(data.text_without_tables.clone()),
+            #[cfg(any(feature = "iwa", feature = "odf"))]
+            _ => self.text(),
+        }
+    }
+}
+
+/// Serializes a summary of the slide: its number, name, title, text, shapes
+/// and tables.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Slide {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        use serde::ser::{Error as _, SerializeStruct};
+
+        let mut state = serializer.serialize_struct("Slide", 6)?;
+        state.serialize_field("number", &self.number())?;
+        state.serialize_field("name", &self.name().map_err(S::Error::custom)?)?;
+        state.serialize_field("title", &self.title().map_err(S::Error::custom)?)?;
+        state.serialize_field("text", &self.text().map_err(S::Error::custom)?)?;
+        state.serialize_field("shapes", &self.shapes().map_err(S::Error::custom)?)?;
+        state.serialize_field("tables", &self.tables().map_err(S::Error::custom)?)?;
+        state.end()
+    }
 }
 
 #[cfg(test)]
@@ -212,4 +413,135 @@ mod tests {
             let _name = slide.name().expect("Failed to get slide name");
         }
     }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_slide_tables_pptx() {
+        use crate::common::Length;
+        use crate::markdown::{MarkdownOptions, TableStyle, ToMarkdown};
+        use crate::ooxml::pptx::Package;
+
+        let mut pkg = Package::new().unwrap();
+        let slide = pkg.presentation_mut().unwrap().add_slide().unwrap();
+        slide.add_text_box("Quarterly results", 0, 0, 3_000_000, 500_000);
+        let table = slide.add_table(
+            2,
+            2,
+            Length::from_inches(1.0),
+            Length::from_inches(1.0),
+            Length::from_inches(4.0),
+            Length::from_inches(1.0),
+        );
+        table.set_cell_text(0, 0, "Region");
+        table.set_cell_text(0, 1, "Sales");
+        table.set_cell_text(1, 0, "North");
+        table.set_cell_text(1, 1, "42");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tables.pptx");
+        pkg.save(&path).unwrap();
+
+        let pres = Presentation::open(&path).expect("Failed to open PPTX");
+        let slides = pres.slides().expect("Failed to get slides");
+        let tables = slides[0].tables().unwrap();
+        assert_eq!(tables.len(), 1);
+
+        let table = &tables[0];
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.column_count(), 2);
+        assert_eq!(table.cell(1, 0).unwrap().text(), "North");
+        assert!(!table.has_merged_cells());
+        assert!(slides[0].charts().unwrap().is_empty());
+
+        let options = MarkdownOptions::default().with_table_style(TableStyle::Markdown);
+        let markdown = slides[0].to_markdown_with_options(&options).unwrap();
+        assert!(markdown.starts_with("Quarterly results"));
+        assert!(markdown.contains("| Region | Sales |"));
+        assert!(markdown.contains("| North | 42 |"));
+
+        let markdown = pres.to_markdown_with_options(&options).unwrap();
+        assert!(markdown.contains("# Slide 1 Quarterly results"));
+        assert!(markdown.contains("| North | 42 |"));
+        assert!(!markdown.contains("\nNorth\n"));
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_slide_title_pptx() {
+        use crate::ooxml::pptx::Package;
+
+        let mut pkg = Package::new().unwrap();
+        let slide = pkg.presentation_mut().unwrap().add_slide().unwrap();
+        slide.add_text_box("Agenda items", 0, 1_000_000, 3_000_000, 500_000);
+        slide.set_title("Agenda");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("title.pptx");
+        pkg.save(&path).unwrap();
+
+        let pres = Presentation::open(&path).expect("Failed to open PPTX");
+        let slides = pres.slides().expect("Failed to get slides");
+        assert_eq!(slides[0].title().unwrap().as_deref(), Some("Agenda"));
+        assert!(slides[0].text().unwrap().contains("Agenda\n"));
+        assert_eq!(slides[0].body_text().unwrap(), "Agenda items");
+    }
+
+    #[test]
+    #[cfg(feature = "ole")]
+    fn test_slide_title_ppt() {
+        let path = test_data_path().join("ole/ppt/SampleShow.ppt");
+        let pres = Presentation::open(&path).expect("Failed to open PPT");
+        let slides = pres.slides().expect("Failed to get slides");
+
+        assert_eq!(
+            slides[0].title().unwrap().as_deref(),
+            Some("Title of the first slide")
+        );
+        assert_eq!(
+            slides[1].title().unwrap().as_deref(),
+            Some("This is the second slide")
+        );
+        for slide in &slides {
+            let title = slide.title().unwrap().unwrap();
+            assert!(slide.text().unwrap().contains(&title));
+            assert!(!slide.body_text().unwrap().contains(&title));
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_slide_shape_bounds_agree() {
+        use crate::common::Rect;
+        use crate::ole::ppt::PptWriter;
+        use crate::ooxml::pptx::Package;
+
+        // 1in x 0.5in text box at (1in, 0.5in) in both formats
+        let expected = Rect::from_emus(914_400, 457_200, 914_400, 457_200);
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut pkg = Package::new().unwrap();
+        let slide = pkg.presentation_mut().unwrap().add_slide().unwrap();
+        slide.add_text_box("Box", 914_400, 457_200, 914_400, 457_200);
+        let pptx_path = dir.path().join("shapes.pptx");
+        pkg.save(&pptx_path).unwrap();
+
+        let mut writer = PptWriter::new();
+        let slide = writer.add_slide().unwrap();
+        writer.add_textbox(slide, 72, 36, 72, 36, "Box").unwrap();
+        let ppt_path = dir.path().join("shapes.ppt");
+        writer.save(&ppt_path).unwrap();
+
+        for path in [pptx_path, ppt_path] {
+            let pres = Presentation::open(&path).expect("Failed to open presentation");
+            let slides = pres.slides().expect("Failed to get slides");
+            let shapes = slides[0].shapes().unwrap();
+            let text_box = shapes
+                .iter()
+                .find(|shape| shape.text() == "Box")
+                .expect("Text box not found");
+            assert_eq!(text_box.bounds(), Some(expected), "{}", path.display());
+            assert_eq!(text_box.rotation(), 0.0);
+            assert!(!text_box.flip_horizontal());
+        }
+    }
 }
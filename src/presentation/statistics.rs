@@ -0,0 +1,76 @@
+//! Slide and word counts for presentations.
+
+use crate::common::Metadata;
+
+/// Slide and word counts of a presentation.
+///
+/// The word counts are computed from the extracted text the same way for
+/// every format; see [`count_words`](crate::common::statistics::count_words).
+/// [`reported_word_count`](Self::reported_word_count) is the value the
+/// application that last saved the file stored in its properties, read
+/// as-is.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Presentation;
+///
+/// let pres = Presentation::open("deck.pptx")?;
+/// let stats = pres.statistics()?;
+/// println!("{} slides, {} words", stats.slide_count(), stats.word_count());
+/// if let Some(words) = stats.notes_word_count() {
+///     println!("{} words of speaker notes", words);
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PresentationStatistics {
+    slide_count: usize,
+    word_count: usize,
+    notes_word_count: Option<usize>,
+    reported_word_count: Option<u32>,
+}
+
+impl PresentationStatistics {
+    pub(crate) fn new(
+        slide_count: usize,
+        word_count: usize,
+        notes_word_count: Option<usize>,
+        metadata: Option<&Metadata>,
+    ) -> Self {
+        Self {
+            slide_count,
+            word_count,
+            notes_word_count,
+            reported_word_count: metadata.and_then(|metadata| metadata.word_count),
+        }
+    }
+
+    /// The number of slides.
+    #[inline]
+    pub fn slide_count(&self) -> usize {
+        self.slide_count
+    }
+
+    /// The number of words on the slides, speaker notes not included.
+    #[inline]
+    pub fn word_count(&self) -> usize {
+        self.word_count
+    }
+
+    /// The number of words in the speaker notes.
+    ///
+    /// `None` for .ppt files, whose notes are not read.
+    #[inline]
+    pub fn notes_word_count(&self) -> Option<usize> {
+        self.notes_word_count
+    }
+
+    /// The number of words reported by the application that saved the file.
+    ///
+    /// PowerPoint counts the words of the speaker notes too.
+    #[inline]
+    pub fn reported_word_count(&self) -> Option<u32> {
+        self.reported_word_count
+    }
+}
@@ -0,0 +1,177 @@
+//! Format-independent tables read from presentation slides.
+
+/// A table on a slide.
+///
+/// Rows are laid out on the table grid: every row holds one cell per grid
+/// column, and cells covered by a merged cell are marked with
+/// [`SlideTableCell::is_merged`].
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::Presentation;
+///
+/// let pres = Presentation::open("presentation.pptx")?;
+/// for slide in pres.slides()? {
+///     for table in slide.tables()? {
+///         for row in table.rows() {
+///             let texts: Vec<&str> = row.iter().map(|cell| cell.text()).collect();
+///             println!("{}", texts.join(" | "));
+///         }
+///     }
+/// }
+/// # Ok::<(), litchi::common::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlideTable {
+    rows: Vec<Vec<SlideTableCell>>,
+}
+
+impl SlideTable {
+    /// Build a table from a PPTX `a:tbl` table.
+    #[cfg(feature = "ooxml")]
+    pub(crate) fn from_pptx(
+        table: &crate::ooxml::pptx::shapes::Table,
+    ) -> crate::common::Result<Self> {
+        let mut rows = Vec::new();
+        for row in table.rows()? {
+            let mut cells = Vec::new();
+            for cell in row.cells()? {
+                cells.push(SlideTableCell {
+                    text: cell.text()?,
+                    row_span: cell.row_span()?.max(1) as usize,
+                    col_span: cell.grid_span()?.max(1) as usize,
+                    merged: cell.is_h_merge()? || cell.is_v_merge()?,
+                });
+            }
+            rows.push(cells);
+        }
+        Ok(Self { rows })
+    }
+
+    /// Build a table from a PPT table group.
+    #[cfg(feature = "ole")]
+    pub(crate) fn from_ppt(table: &crate::ole::ppt::shapes::shape_enum::TableShape) -> Self {
+        let rows = (0..table.rows())
+            .map(|row| {
+                (0..table.columns())
+                    .map(|col| {
+                        let (row_span, col_span) = table.cell_span(row, col).unwrap_or((1, 1));
+                        SlideTableCell {
+                            text: table.cell(row, col).unwrap_or_default().to_string(),
+                            row_span: row_span.max(1),
+                            col_span: col_span.max(1),
+                            merged: row_span == 0,
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Self { rows }
+    }
+
+    /// Get the rows of the table.
+    #[inline]
+    pub fn rows(&self) -> &[Vec<SlideTableCell>] {
+        &self.rows
+    }
+
+    /// Get the number of rows.
+    #[inline]
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Get the number of grid columns.
+    pub fn column_count(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    /// Get the cell at the given grid position.
+    pub fn cell(&self, row: usize, col: usize) -> Option<&SlideTableCell> {
+        self.rows.get(row).and_then(|cells| cells.get(col))
+    }
+
+    /// Check whether any cells of the table are merged.
+    pub fn has_merged_cells(&self) -> bool {
+        self.rows
+            .iter()
+            .flatten()
+            .any(|cell| cell.merged || cell.row_span > 1 || cell.col_span > 1)
+    }
+}
+
+/// A cell of a [`SlideTable`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SlideTableCell {
+    text: String,
+    row_span: usize,
+    col_span: usize,
+    merged: bool,
+}
+
+impl SlideTableCell {
+    /// Get the text of the cell.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Get the number of rows spanned by the cell.
+    #[inline]
+    pub fn row_span(&self) -> usize {
+        self.row_span
+    }
+
+    /// Get the number of columns spanned by the cell.
+    #[inline]
+    pub fn col_span(&self) -> usize {
+        self.col_span
+    }
+
+    /// Check whether the cell is covered by a merged cell.
+    ///
+    /// Covered cells carry no content of their own.
+    #[inline]
+    pub fn is_merged(&self) -> bool {
+        self.merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(text: &str, row_span: usize, col_span: usize, merged: bool) -> SlideTableCell {
+        SlideTableCell {
+            text: text.to_string(),
+            row_span,
+            col_span,
+            merged,
+        }
+    }
+
+    #[test]
+    fn test_table_dimensions_and_merges() {
+        let table = SlideTable {
+            rows: vec![
+                vec![cell("Header", 1, 2, false), cell("", 1, 1, true)],
+                vec![cell("A", 1, 1, false), cell("B", 1, 1, false)],
+            ],
+        };
+
+        assert_eq!(table.row_count(), 2);
+        assert_eq!(table.column_count(), 2);
+        assert_eq!(table.cell(0, 0).map(SlideTableCell::text), Some("Header"));
+        assert!(table.cell(0, 1).unwrap().is_merged());
+        assert!(table.has_merged_cells());
+
+        let plain = SlideTable {
+            rows: vec![vec![cell("A", 1, 1, false)]],
+        };
+        assert!(!plain.has_merged_cells());
+        assert!(plain.cell(1, 0).is_none());
+    }
+}
@@ -1,11 +1,17 @@
 //! Internal types for presentation format detection and implementation.
 
+use super::shape::SlideShape;
+use super::table::SlideTable;
 use crate::common::detection::{self, FileFormat};
 use crate::common::{Error, Result};
 use std::io::{Read, Seek};
 
 #[cfg(feature = "ole")]
 use crate::ole;
+#[cfg(feature = "ole")]
+use once_cell::sync::OnceCell;
+#[cfg(feature = "ole")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 #[cfg(feature = "ooxml")]
 use crate::ooxml;
@@ -15,6 +21,73 @@ use crate::ooxml;
 pub struct PptxSlideData {
     pub text: String,
     pub name: Option<String>,
+    pub title: Option<String>,
+    /// Slide text without the title
+    pub body_text: String,
+    /// Slide text without the text of its tables
+    pub text_without_tables: String,
+    pub tables: Vec<SlideTable>,
+    pub shapes: Vec<SlideShape>,
+    #[cfg(feature = "ooxml")]
+    pub charts: Vec<ooxml::charts::Chart>,
+    #[cfg(feature = "ooxml")]
+    pub smartart: Vec<ooxml::pptx::SmartArt>,
+}
+
+/// Content of a slide for markdown conversion.
+#[derive(Debug, Clone)]
+pub struct SlideContent {
+    /// Slide number (1-based)
+    pub number: usize,
+    /// Slide text without the text of its tables and SmartArt
+    pub text: String,
+    pub tables: Vec<SlideTable>,
+    #[cfg(feature = "ooxml")]
+    pub smartart: Vec<ooxml::pptx::SmartArt>,
+}
+
+/// Content of a slide for HTML export, with the geometry of each shape.
+#[cfg(feature = "html")]
+#[derive(Debug, Clone)]
+pub struct PositionedSlide {
+    /// Slide number (1-based)
+    pub number: usize,
+    /// Shapes in drawing order
+    pub shapes: Vec<PositionedShape>,
+    /// Speaker notes
+    pub notes: Option<String>,
+}
+
+/// A shape and its content, placed on a slide.
+#[cfg(feature = "html")]
+#[derive(Debug, Clone, Default)]
+pub struct PositionedShape {
+    /// Unrotated frame of the shape, `None` if it has no position
+    pub bounds: Option<crate::common::Rect>,
+    /// Clockwise rotation in degrees
+    pub rotation: f64,
+    pub flip_horizontal: bool,
+    pub flip_vertical: bool,
+    /// Solid fill color
+    pub fill: Option<crate::common::RGBColor>,
+    /// Paragraphs of the shape text
+    pub paragraphs: Vec<Vec<StyledRun>>,
+    /// Picture data and its file extension
+    pub image: Option<(Vec<u8>, &'static str)>,
+    pub table: Option<SlideTable>,
+}
+
+/// A run of shape text with its character formatting.
+#[cfg(feature = "html")]
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StyledRun {
+    pub text: String,
+    /// Font size in points
+    pub font_size: Option<f64>,
+    pub color: Option<crate::common::RGBColor>,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
 }
 
 /// Extracted data from a PPT slide (to avoid lifetime issues).
@@ -23,6 +96,13 @@ pub struct PptSlideData {
     pub text: String,
     pub slide_number: usize,
     pub shape_count: usize,
+    pub title: Option<String>,
+    /// Slide text without the title
+    pub body_text: String,
+    /// Slide text without the text of its tables
+    pub text_without_tables: String,
+    pub tables: Vec<SlideTable>,
+    pub shapes: Vec<SlideShape>,
 }
 
 /// A PowerPoint presentation implementation that can be .ppt, .pptx, .key, or .odp format.
@@ -34,7 +114,7 @@ pub struct PptSlideData {
 pub(super) enum PresentationImpl {
     /// Legacy .ppt format
     #[cfg(feature = "ole")]
-    Ppt(ole::ppt::Presentation),
+    Ppt(PptFile),
     /// Modern .pptx format
     #[cfg(feature = "ooxml")]
     Pptx(Box<ooxml::pptx::Presentation<'static>>),
@@ -46,6 +126,38 @@ pub(super) enum PresentationImpl {
     Odp(crate::odf::Presentation),
 }
 
+/// An open .ppt file whose presentation is parsed on first use.
+///
+/// The package is kept open so that slide text can be streamed from it
+/// without parsing the whole presentation.
+#[cfg(feature = "ole")]
+pub(super) struct PptFile<R: Read + Seek = ole::OleSource> {
+    package: Mutex<ole::ppt::Package<R>>,
+    presentation: OnceCell<ole::ppt::Presentation>,
+}
+
+#[cfg(feature = "ole")]
+impl<R: Read + Seek> PptFile<R> {
+    pub(super) fn new(package: ole::ppt::Package<R>) -> Self {
+        Self {
+            package: Mutex::new(package),
+            presentation: OnceCell::new(),
+        }
+    }
+
+    /// Get the parsed presentation, parsing it on the first call.
+    pub(super) fn presentation(&self) -> Result<&ole::ppt::Presentation> {
+        self.presentation
+            .get_or_try_init(|| self.package().presentation().map_err(Error::from))
+    }
+
+    /// Lock the package for reading its streams.
+    pub(super) fn package(&self) -> MutexGuard<'_, ole::ppt::Package<R>> {
+        // A panic while reading leaves the reader usable, since every read seeks first
+        self.package.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
 /// Presentation format detection.
 ///
 /// This enum represents the supported presentation formats in the unified
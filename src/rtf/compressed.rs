@@ -134,10 +134,19 @@ pub fn decompress(data: &[u8]) -> RtfResult<Vec<u8>> {
     }
 }
 
+/// CRC-32 used by the LZFu header.
+///
+/// This is the reflected 0x04C11DB7 polynomial like CRC-32/ISO-HDLC, but the
+/// register starts at zero and the result is not inverted ([MS-OXRTFCP] 2.1.3.2).
+fn lzfu_crc32(data: &[u8]) -> u32 {
+    let params = crc_fast::CrcParams::new("CRC-32/LZFU", 32, 0x04C1_1DB7, 0, true, 0, 0x2DFD_2D88);
+    crc_fast::checksum_with_params(params, data) as u32
+}
+
 /// Decompress LZFu compressed data
 fn decompress_lzfu(data: &[u8], header: &CompressedRtfHeader) -> RtfResult<Vec<u8>> {
     // Verify CRC32
-    let calculated_crc = crc_fast::checksum(crc_fast::CrcAlgorithm::Crc32IsoHdlc, data) as u32;
+    let calculated_crc = lzfu_crc32(data);
 
     if calculated_crc != header.get_crc32() {
         return Err(RtfError::InvalidStructure(format!(
@@ -306,7 +315,7 @@ fn compress_lzfu(data: &[u8]) -> RtfResult<Vec<u8>> {
     }
 
     // Calculate CRC32
-    let crc32 = crc_fast::checksum(crc_fast::CrcAlgorithm::Crc32IsoHdlc, &output) as u32;
+    let crc32 = lzfu_crc32(&output);
 
     // Build header
     let header = CompressedRtfHeader::new(
@@ -435,4 +444,20 @@ mod tests {
         let decompressed = decompress(&compressed).unwrap();
         assert_eq!(original, decompressed.as_slice());
     }
+
+    #[test]
+    fn test_decompress_spec_sample() {
+        // [MS-OXRTFCP] 4.1.1: "{\rtf1\ansi\ansicpg1252\pard hello world}\r\n"
+        let compressed = [
+            0x2d, 0x00, 0x00, 0x00, 0x2b, 0x00, 0x00, 0x00, 0x4c, 0x5a, 0x46, 0x75, 0xf1, 0xc5,
+            0xc7, 0xa7, 0x03, 0x00, 0x0a, 0x00, 0x72, 0x63, 0x70, 0x67, 0x31, 0x32, 0x35, 0x42,
+            0x32, 0x0a, 0xf3, 0x20, 0x68, 0x65, 0x6c, 0x09, 0x00, 0x20, 0x62, 0x77, 0x05, 0xb0,
+            0x6c, 0x64, 0x7d, 0x0a, 0x80, 0x0f, 0xa0,
+        ];
+        let decompressed = decompress(&compressed).unwrap();
+        assert_eq!(
+            decompressed,
+            b"{\\rtf1\\ansi\\ansicpg1252\\pard hello world}\r\n"
+        );
+    }
 }
@@ -4,6 +4,7 @@ use super::error::{RtfError, RtfResult};
 use super::lexer::Lexer;
 use super::parser::Parser;
 use super::types::{ColorTable, FontTable, Paragraph as RtfParagraph, Run, StyleBlock};
+use crate::common::Language;
 use bumpalo::Bump;
 use std::borrow::Cow;
 use std::path::Path;
@@ -47,6 +48,10 @@ pub struct RtfDocument<'a> {
     notes: Vec<super::section::Note<'a>>,
     /// Track changes/revisions
     revisions: Vec<super::annotation::Revision<'a>>,
+    /// Document default languages
+    default_language: Language,
+    /// Document protection
+    protection: super::info::DocumentProtection,
 }
 
 impl<'a> RtfDocument<'a> {
@@ -107,13 +112,24 @@ impl<'a> RtfDocument<'a> {
 
         // Convert parsed document to owned document
         // We need to convert Cow::Borrowed to Cow::Owned to detach from input lifetime
+        // Text that does not set its language uses the document defaults
+        let (default_lang, default_lang_fe) = parsed.default_language;
         let owned_blocks: Vec<StyleBlock<'static>> = parsed
             .blocks
             .into_iter()
-            .map(|block| StyleBlock {
-                text: Cow::Owned(block.text.into_owned()),
-                formatting: block.formatting,
-                paragraph: block.paragraph,
+            .map(|block| {
+                let mut formatting = block.formatting;
+                if formatting.language == 0 {
+                    formatting.language = default_lang;
+                }
+                if formatting.language_fe == 0 {
+                    formatting.language_fe = default_lang_fe;
+                }
+                StyleBlock {
+                    text: Cow::Owned(block.text.into_owned()),
+                    formatting,
+                    paragraph: block.paragraph,
+                }
             })
             .collect();
 
@@ -196,6 +212,8 @@ impl<'a> RtfDocument<'a> {
             annotations: Self::convert_annotations_to_owned(parsed.annotations),
             notes: Self::convert_notes_to_owned(parsed.notes),
             revisions: Self::convert_revisions_to_owned(parsed.revisions),
+            default_language: Language::from_lcids(Some(default_lang), Some(default_lang_fe), None),
+            protection: parsed.protection,
         })
     }
 
@@ -507,6 +525,19 @@ impl<'a> RtfDocument<'a> {
         &self.stylesheet
     }
 
+    /// Get the document default languages (`\deflang`, `\deflangfe`) as
+    /// BCP-47 tags.
+    ///
+    /// RTF has no default for complex script text.
+    pub fn default_language(&self) -> &Language {
+        &self.default_language
+    }
+
+    /// Get the document protection (`\protlevel`, `\enforceprot`).
+    pub fn protection(&self) -> &super::info::DocumentProtection {
+        &self.protection
+    }
+
     /// Get document information/metadata.
     ///
     /// Returns document properties like title, author, subject, etc.
@@ -118,6 +118,9 @@ pub struct DocumentProtection {
     pub protection_type: ProtectionType,
     /// Whether protection is enforced
     pub enforced: bool,
+    /// Whether a password is needed to lift the protection
+    /// (`{\*\passwordhash}`)
+    pub has_password: bool,
 }
 
 impl DocumentProtection {
@@ -127,6 +130,7 @@ impl DocumentProtection {
         Self {
             protection_type,
             enforced: true,
+            has_password: false,
         }
     }
 
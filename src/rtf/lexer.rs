@@ -19,6 +19,15 @@ pub enum ControlWord<'a> {
     Rtf(i32),
     Ansi,
     AnsiCodePage(i32),
+    DefaultLanguage(i32),
+    DefaultLanguageFe(i32),
+    // Document protection
+    FormProtection,
+    RevisionProtection,
+    AnnotationProtection,
+    ReadOnlyProtection,
+    ProtectionLevel(i32),
+    EnforceProtection(i32),
     Mac,
     Pc,
     Pca,
@@ -100,6 +109,11 @@ pub enum ControlWord<'a> {
     CharScale(i32),
     Kerning(i32),
     Highlight(i32),
+    Language(i32),
+    LanguageFe(i32),
+    AssociatedLanguage(i32),
+    RightToLeftChar,
+    LeftToRightChar,
     Plain,
 
     // Paragraph formatting
@@ -124,6 +138,8 @@ pub enum ControlWord<'a> {
     KeepNext,
     PageBreakBefore,
     WidowControl,
+    RightToLeftParagraph,
+    LeftToRightParagraph,
 
     // Tables
     TableRowDefaults,
@@ -327,6 +343,13 @@ impl<'a> Lexer<'a> {
         let mut tokens = Vec::new();
 
         while self.pos < self.input.len() {
+            // Line breaks or NUL padding after the last group carry no content
+            if self.input[self.pos..]
+                .trim_start_matches(|c: char| c.is_whitespace() || c == '\0')
+                .is_empty()
+            {
+                break;
+            }
             let token = self.next_token()?;
             tokens.push(token);
         }
@@ -481,6 +504,14 @@ impl<'a> Lexer<'a> {
             "rtf" => ControlWord::Rtf(param_value),
             "ansi" => ControlWord::Ansi,
             "ansicpg" => ControlWord::AnsiCodePage(param_value),
+            "deflang" => ControlWord::DefaultLanguage(param_value),
+            "deflangfe" => ControlWord::DefaultLanguageFe(param_value),
+            "formprot" => ControlWord::FormProtection,
+            "revprot" => ControlWord::RevisionProtection,
+            "annotprot" => ControlWord::AnnotationProtection,
+            "readprot" => ControlWord::ReadOnlyProtection,
+            "protlevel" => ControlWord::ProtectionLevel(param.unwrap_or(0)),
+            "enforceprot" => ControlWord::EnforceProtection(param_value),
             "mac" => ControlWord::Mac,
             "pc" => ControlWord::Pc,
             "pca" => ControlWord::Pca,
@@ -568,6 +599,11 @@ impl<'a> Lexer<'a> {
             "charscalex" => ControlWord::CharScale(param_value),
             "kerning" => ControlWord::Kerning(param_value),
             "highlight" => ControlWord::Highlight(param_value),
+            "lang" => ControlWord::Language(param_value),
+            "langfe" => ControlWord::LanguageFe(param_value),
+            "alang" => ControlWord::AssociatedLanguage(param_value),
+            "rtlch" => ControlWord::RightToLeftChar,
+            "ltrch" => ControlWord::LeftToRightChar,
             "plain" => ControlWord::Plain,
 
             // Paragraph
@@ -592,6 +628,8 @@ impl<'a> Lexer<'a> {
             "keepn" => ControlWord::KeepNext,
             "pagebb" => ControlWord::PageBreakBefore,
             "widctlpar" => ControlWord::WidowControl,
+            "rtlpar" => ControlWord::RightToLeftParagraph,
+            "ltrpar" => ControlWord::LeftToRightParagraph,
 
             // Tables
             "trowd" => ControlWord::TableRowDefaults,
@@ -893,6 +931,17 @@ mod tests {
         assert!(matches!(tokens[2], Token::Control(ControlWord::Ansi)));
     }
 
+    #[test]
+    fn test_trailing_line_breaks_ignored() {
+        let arena = Bump::new();
+        let input = "{\\rtf1\\ansi Hello}\r\n\0";
+        let mut lexer = Lexer::new(input, &arena);
+        let tokens = lexer.tokenize().unwrap();
+
+        assert_eq!(tokens.len(), 5);
+        assert!(matches!(tokens[4], Token::CloseBrace));
+    }
+
     #[test]
     fn test_lexer_new() {
         let arena = Bump::new();
@@ -1,6 +1,7 @@
 //! RTF parser that builds document structure from tokens.
 
 use super::error::{RtfError, RtfResult};
+use super::info::{DocumentProtection, ProtectionType};
 use super::lexer::{ControlWord, Token};
 use super::types::*;
 use crate::common::encoding::codepage_to_encoding;
@@ -139,6 +140,11 @@ pub struct Parser<'a> {
     current_note_buffer: SmallVec<[u8; 256]>,
     /// Current header/footer type being parsed
     current_hf_type: Option<super::section::HeaderFooterType>,
+    /// Default Latin and East Asian language identifiers (`\deflang`, `\deflangfe`)
+    default_language: (u16, u16),
+    /// Document protection (`\protlevel`, `\enforceprot` and the older
+    /// `\formprot`, `\revprot`, `\annotprot`, `\readprot`)
+    protection: DocumentProtection,
 }
 
 impl<'a> Parser<'a> {
@@ -172,6 +178,8 @@ impl<'a> Parser<'a> {
             current_header_footer: None,
             current_note_buffer: SmallVec::new(),
             current_hf_type: None,
+            default_language: (0, 0),
+            protection: DocumentProtection::default(),
         }
     }
 
@@ -215,6 +223,8 @@ impl<'a> Parser<'a> {
             annotations: self.annotations,
             notes: self.notes,
             revisions: self.revisions,
+            default_language: self.default_language,
+            protection: self.protection,
         })
     }
 
@@ -253,6 +263,16 @@ impl<'a> Parser<'a> {
                     return Ok(());
                 },
                 Token::Control(ControlWord::IgnorableDestination) => {
+                    // The protection password is only recorded, not read
+                    if matches!(
+                        self.tokens.get(self.pos + 1),
+                        Some(Token::Control(ControlWord::Unknown(
+                            "passwordhash" | "password",
+                            _
+                        )))
+                    ) {
+                        self.protection.has_password = true;
+                    }
                     // Mark as other destination and skip
                     if let Some(state) = self.states.last_mut() {
                         state.destination = Destination::Other;
@@ -626,6 +646,11 @@ impl<'a> Parser<'a> {
             ControlWord::CharScale(n) => state.formatting.char_scale = *n,
             ControlWord::Kerning(n) => state.formatting.kerning = *n,
             ControlWord::Highlight(c) => state.formatting.highlight_color = Some(*c as ColorRef),
+            ControlWord::Language(n) => state.formatting.language = *n as u16,
+            ControlWord::LanguageFe(n) => state.formatting.language_fe = *n as u16,
+            ControlWord::AssociatedLanguage(n) => state.formatting.language_complex = *n as u16,
+            ControlWord::RightToLeftChar => state.formatting.rtl = true,
+            ControlWord::LeftToRightChar => state.formatting.rtl = false,
             ControlWord::Plain => {
                 // Reset to default formatting
                 state.formatting = Formatting::default();
@@ -657,6 +682,8 @@ impl<'a> Parser<'a> {
             ControlWord::KeepNext => state.paragraph.keep_next = true,
             ControlWord::PageBreakBefore => state.paragraph.page_break_before = true,
             ControlWord::WidowControl => state.paragraph.widow_control = true,
+            ControlWord::RightToLeftParagraph => state.paragraph.rtl = true,
+            ControlWord::LeftToRightParagraph => state.paragraph.rtl = false,
 
             // Unicode
             ControlWord::UnicodeSkip(n) => state.unicode_skip = *n,
@@ -675,6 +702,31 @@ impl<'a> Parser<'a> {
                 }
             },
 
+            // Document default languages
+            ControlWord::DefaultLanguage(n) => self.default_language.0 = *n as u16,
+            ControlWord::DefaultLanguageFe(n) => self.default_language.1 = *n as u16,
+
+            // Document protection. The older words imply enforcement and
+            // are refined by \protlevel when both are present.
+            ControlWord::FormProtection => self.set_legacy_protection(ProtectionType::Forms),
+            ControlWord::RevisionProtection => {
+                self.set_legacy_protection(ProtectionType::RevisionTracking)
+            },
+            ControlWord::AnnotationProtection => {
+                self.set_legacy_protection(ProtectionType::Comments)
+            },
+            ControlWord::ReadOnlyProtection => self.set_legacy_protection(ProtectionType::ReadOnly),
+            ControlWord::ProtectionLevel(n) => {
+                self.protection.protection_type = match n {
+                    0 => ProtectionType::RevisionTracking,
+                    1 => ProtectionType::Comments,
+                    2 => ProtectionType::Forms,
+                    3 => ProtectionType::ReadOnly,
+                    _ => ProtectionType::None,
+                };
+            },
+            ControlWord::EnforceProtection(n) => self.protection.enforced = *n != 0,
+
             // Table control words
             ControlWord::InTable => {
                 state.in_table = true;
@@ -840,6 +892,25 @@ impl<'a> Parser<'a> {
         Ok(())
     }
 
+    /// Record a protection type set by one of the older control words,
+    /// keeping the strongest when several are present.
+    fn set_legacy_protection(&mut self, protection_type: ProtectionType) {
+        let rank = |protection_type| match protection_type {
+            ProtectionType::None => 0,
+            ProtectionType::RevisionTracking => 1,
+            ProtectionType::Comments => 2,
+            ProtectionType::Forms => 3,
+            ProtectionType::ReadOnly => 4,
+        };
+        if rank(protection_type) >= rank(self.protection.protection_type) {
+            self.protection = DocumentProtection {
+                protection_type,
+                enforced: true,
+                ..self.protection
+            };
+        }
+    }
+
     /// Skip tokens until closing brace.
     fn skip_until_close_brace(&mut self) -> RtfResult<()> {
         let mut depth = 1;
@@ -1426,4 +1497,8 @@ pub struct ParsedDocument<'a> {
     pub notes: Vec<super::section::Note<'a>>,
     /// Track changes/revisions
     pub revisions: Vec<super::annotation::Revision<'a>>,
+    /// Default Latin and East Asian language identifiers, 0 if not set
+    pub default_language: (u16, u16),
+    /// Document protection
+    pub protection: DocumentProtection,
 }
@@ -1,6 +1,7 @@
 //! RTF document type definitions.
 
 use super::border::{Borders, Shading};
+use crate::common::Language;
 use std::borrow::Cow;
 use std::num::NonZeroU16;
 
@@ -228,6 +229,8 @@ pub struct Paragraph {
     pub page_break_before: bool,
     /// Widow/orphan control
     pub widow_control: bool,
+    /// Right-to-left paragraph (`\rtlpar`)
+    pub rtl: bool,
 }
 
 /// Underline style
@@ -301,6 +304,14 @@ pub struct Formatting {
     pub char_scale: i32,
     /// Kerning (in half-points)
     pub kerning: i32,
+    /// Language identifier (LCID) of Latin text (`\lang`), 0 if not set
+    pub language: u16,
+    /// Language identifier of East Asian text (`\langfe`), 0 if not set
+    pub language_fe: u16,
+    /// Language identifier of complex script text (`\alang`), 0 if not set
+    pub language_complex: u16,
+    /// Right-to-left text (`\rtlch`)
+    pub rtl: bool,
 }
 
 impl Default for Formatting {
@@ -328,6 +339,10 @@ impl Default for Formatting {
             char_spacing: 0,
             char_scale: 100,
             kerning: 0,
+            language: 0,
+            language_fe: 0,
+            language_complex: 0,
+            rtl: false,
         }
     }
 }
@@ -389,6 +404,19 @@ impl<'a> Run<'a> {
             None
         }
     }
+
+    /// Get the declared languages of this run as BCP-47 tags.
+    ///
+    /// Languages the run does not set are the document defaults
+    /// (`\deflang`, `\deflangfe`) for runs of a parsed document.
+    pub fn language(&self) -> Language {
+        let lcid = |lcid: u16| (lcid != 0).then_some(lcid);
+        Language::from_lcids(
+            lcid(self.formatting.language),
+            lcid(self.formatting.language_fe),
+            lcid(self.formatting.language_complex),
+        )
+    }
 }
 
 /// A styled block of text with paragraph and character formatting.
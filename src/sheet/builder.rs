@@ -0,0 +1,307 @@
+//! Building new .xlsx workbooks from scratch.
+//!
+//! [`WorkbookBuilder`] is the write-side counterpart to the unified
+//! [`Workbook`](super::Workbook) reader. It wraps the XLSX writer in
+//! [`crate::ooxml::xlsx`] and takes care of the details that are easy to get
+//! wrong when producing a file by hand, such as giving date cells a date
+//! number format so they do not show up as bare serial numbers.
+
+use std::io::{Cursor, Seek, Write};
+use std::path::Path;
+
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+
+use super::DateSystem;
+use super::types::{CellValue, Result};
+use crate::ooxml::xlsx::{CellFormat, MutableWorksheet, Workbook as XlsxWorkbook};
+
+/// Number format applied to cells written with [`WorksheetBuilder::set_datetime`]
+/// and to [`CellValue::DateTime`] values.
+const DATETIME_FORMAT: &str = "yyyy-mm-dd hh:mm:ss";
+
+/// Builder for a new .xlsx workbook.
+///
+/// The first call to [`add_worksheet`](Self::add_worksheet) names the
+/// workbook's initial sheet; later calls append new sheets. A workbook with
+/// no added worksheets is saved with a single empty `Sheet1`.
+///
+/// Row and column numbers are 1-based.
+///
+/// # Examples
+///
+/// ```rust
+/// use chrono::NaiveDate;
+/// use litchi::sheet::WorkbookBuilder;
+///
+/// let mut builder = WorkbookBuilder::new()?;
+/// builder
+///     .add_worksheet("Sales")
+///     .set_value(1, 1, "Region")
+///     .set_value(1, 2, "Amount")
+///     .set_value(2, 1, "North")
+///     .set_value(2, 2, 1250.5)
+///     .set_date(2, 3, NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+///     .set_formula(3, 2, "SUM(B2:B2)")
+///     .set_column_width(1, 20.0);
+///
+/// let bytes = builder.to_bytes()?;
+/// assert!(!bytes.is_empty());
+/// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+#[derive(Debug)]
+pub struct WorkbookBuilder {
+    workbook: XlsxWorkbook,
+    worksheet_count: usize,
+}
+
+impl WorkbookBuilder {
+    /// Create a builder for an empty workbook.
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            workbook: XlsxWorkbook::create()?,
+            worksheet_count: 0,
+        })
+    }
+
+    /// Add a worksheet and return a builder for its contents.
+    pub fn add_worksheet(&mut self, name: &str) -> WorksheetBuilder<'_> {
+        let sheet = if self.worksheet_count == 0 {
+            let sheet = self
+                .workbook
+                .worksheet_mut(0)
+                .expect("a new workbook always has one worksheet");
+            sheet.set_name(name.to_string());
+            sheet
+        } else {
+            self.workbook.add_worksheet(name)
+        };
+        self.worksheet_count += 1;
+        WorksheetBuilder { sheet }
+    }
+
+    /// Write the workbook to an .xlsx file.
+    pub fn save<P: AsRef<Path>>(&mut self, path: P) -> Result<()> {
+        self.workbook.save(path)
+    }
+
+    /// Write the workbook to a stream.
+    pub fn to_stream<W: Write + Seek>(&mut self, writer: W) -> Result<()> {
+        self.workbook.to_stream(writer)
+    }
+
+    /// Serialize the workbook to .xlsx bytes.
+    pub fn to_bytes(&mut self) -> Result<Vec<u8>> {
+        let mut cursor = Cursor::new(Vec::new());
+        self.to_stream(&mut cursor)?;
+        Ok(cursor.into_inner())
+    }
+
+    /// Access the underlying XLSX workbook for features the builder does not
+    /// cover (named ranges, protection, document properties, ...).
+    pub fn workbook_mut(&mut self) -> &mut XlsxWorkbook {
+        &mut self.workbook
+    }
+
+    /// Consume the builder and return the underlying XLSX workbook.
+    pub fn into_inner(self) -> XlsxWorkbook {
+        self.workbook
+    }
+}
+
+/// Builder for the contents of one worksheet, returned by
+/// [`WorkbookBuilder::add_worksheet`].
+#[derive(Debug)]
+pub struct WorksheetBuilder<'a> {
+    sheet: &'a mut MutableWorksheet,
+}
+
+impl WorksheetBuilder<'_> {
+    /// Set a cell value.
+    ///
+    /// Strings go into the shared strings table. [`CellValue::DateTime`]
+    /// serials are given a date-time number format.
+    pub fn set_value<V: Into<CellValue>>(&mut self, row: u32, col: u32, value: V) -> &mut Self {
+        let value = value.into();
+        if matches!(value, CellValue::DateTime(_)) {
+            self.set_number_format(row, col, DATETIME_FORMAT);
+        }
+        self.sheet.set_cell_value(row, col, value);
+        self
+    }
+
+    /// Set a cell value with a format, such as [`CellFormat::bold_header`].
+    pub fn set_value_with_format<V: Into<CellValue>>(
+        &mut self,
+        row: u32,
+        col: u32,
+        value: V,
+        format: &CellFormat,
+    ) -> &mut Self {
+        self.sheet.set_cell_with_format(row, col, value, format);
+        self
+    }
+
+    /// Set a cell formula, with or without a leading `=`.
+    ///
+    /// No cached result is stored; spreadsheet applications compute it when
+    /// the file is opened.
+    pub fn set_formula(&mut self, row: u32, col: u32, formula: &str) -> &mut Self {
+        let formula = formula.strip_prefix('=').unwrap_or(formula);
+        self.sheet.set_cell_formula(row, col, formula);
+        self
+    }
+
+    /// Set a date cell, formatted as `yyyy-mm-dd`.
+    pub fn set_date(&mut self, row: u32, col: u32, date: NaiveDate) -> &mut Self {
+        self.sheet.set_cell_with_format(
+            row,
+            col,
+            CellValue::DateTime(date_serial(date)),
+            &CellFormat::date(),
+        );
+        self
+    }
+
+    /// Set a date-time cell, formatted as `yyyy-mm-dd hh:mm:ss`.
+    pub fn set_datetime(&mut self, row: u32, col: u32, datetime: NaiveDateTime) -> &mut Self {
+        let seconds = datetime.time().num_seconds_from_midnight() as f64;
+        let serial = date_serial(datetime.date()) + seconds / 86_400.0;
+        self.set_value(row, col, CellValue::DateTime(serial))
+    }
+
+    /// Merge a rectangular range of cells.
+    pub fn merge_cells(
+        &mut self,
+        start_row: u32,
+        start_col: u32,
+        end_row: u32,
+        end_col: u32,
+    ) -> &mut Self {
+        self.sheet
+            .merge_cells(start_row, start_col, end_row, end_col);
+        self
+    }
+
+    /// Set a column width in characters.
+    pub fn set_column_width(&mut self, col: u32, width: f64) -> &mut Self {
+        self.sheet.set_column_width(col, width);
+        self
+    }
+
+    /// Access the underlying worksheet writer for features the builder does
+    /// not cover.
+    pub fn worksheet_mut(&mut self) -> &mut MutableWorksheet {
+        self.sheet
+    }
+
+    fn set_number_format(&mut self, row: u32, col: u32, format: &str) {
+        self.sheet.set_cell_format(
+            row,
+            col,
+            CellFormat {
+                number_format: Some(format.to_string()),
+                ..Default::default()
+            },
+        );
+    }
+}
+
+/// Convert a date to its serial number in the 1900 date system.
+fn date_serial(date: NaiveDate) -> f64 {
+    DateSystem::Excel1900.date_to_serial(date)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ooxml::opc::OpcPackage;
+    use crate::sheet::WorkbookTrait;
+
+    #[test]
+    fn builder_round_trips_through_reader() {
+        let mut builder = WorkbookBuilder::new().unwrap();
+        builder
+            .add_worksheet("Data")
+            .set_value(1, 1, "Name")
+            .set_value(1, 2, "Score")
+            .set_value(2, 1, "Ada & Co")
+            .set_value(2, 2, 42)
+            .set_value(3, 1, "Grace")
+            .set_value(3, 2, 97.5)
+            .set_value(4, 1, true)
+            .set_date(5, 1, NaiveDate::from_ymd_opt(2024, 2, 29).unwrap())
+            .set_formula(6, 2, "=SUM(B2:B3)")
+            .merge_cells(7, 1, 7, 3)
+            .set_value(7, 1, "Merged")
+            .set_value_with_format(8, 1, "Total", &CellFormat::bold_header())
+            .set_value_with_format(8, 2, 0.125, &CellFormat::percent())
+            .set_column_width(1, 24.0);
+        builder.add_worksheet("Second").set_value(1, 1, "B");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("built.xlsx");
+        builder.save(&path).unwrap();
+
+        let workbook = XlsxWorkbook::open(&path).unwrap();
+        assert_eq!(workbook.worksheet_names(), ["Data", "Second"]);
+
+        let sheet = workbook.worksheet(0).unwrap();
+        let values = sheet.row_values(2).unwrap();
+        assert_eq!(values[0], CellValue::String("Ada & Co".to_string()));
+        assert_eq!(values[1], CellValue::Int(42));
+        assert_eq!(sheet.row_values(3).unwrap()[1], CellValue::Float(97.5));
+        assert_eq!(sheet.row_values(4).unwrap()[0], CellValue::Bool(true));
+
+        // 2024-02-29 is serial 45351 in the 1900 date system
+        assert!(sheet.is_date_formatted(5, 1));
+        assert_eq!(sheet.get_date_cell_value(5, 1), Some(45351.0));
+
+        match &sheet.row_values(6).unwrap()[1] {
+            CellValue::Formula {
+                formula,
+                cached_value,
+                ..
+            } => {
+                assert_eq!(formula, "SUM(B2:B3)");
+                assert!(cached_value.is_none());
+            },
+            other => panic!("expected formula, got {other:?}"),
+        }
+
+        assert_eq!(sheet.get_merged_regions(), &[(7, 1, 7, 3)]);
+        assert_eq!(sheet.get_column_width(1), Some(24.0));
+
+        let header = sheet.get_cell_format(8, 1).unwrap();
+        assert!(header.font.unwrap().bold);
+        assert!(header.border.unwrap().bottom.is_some());
+        let percent = sheet.get_cell_format(8, 2).unwrap();
+        assert_eq!(percent.number_format.as_deref(), Some("0.00%"));
+
+        let second = workbook.worksheet(1).unwrap();
+        assert_eq!(
+            second.row_values(1).unwrap()[0],
+            CellValue::String("B".to_string())
+        );
+    }
+
+    #[test]
+    fn datetime_values_get_a_date_format() {
+        let mut builder = WorkbookBuilder::new().unwrap();
+        let noon = NaiveDate::from_ymd_opt(2000, 1, 1)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        builder
+            .add_worksheet("Times")
+            .set_datetime(1, 1, noon)
+            .set_value(2, 1, CellValue::DateTime(1.25));
+
+        let bytes = builder.to_bytes().unwrap();
+        let package = OpcPackage::from_reader(std::io::Cursor::new(bytes)).unwrap();
+        let workbook = XlsxWorkbook::new(package).unwrap();
+        let sheet = workbook.worksheet(0).unwrap();
+        assert!(sheet.is_date_formatted(1, 1));
+        assert_eq!(sheet.get_date_cell_value(1, 1), Some(36526.5));
+        assert!(sheet.is_date_formatted(2, 1));
+    }
+}
@@ -0,0 +1,358 @@
+//! Conversion between dates and spreadsheet serial numbers.
+//!
+//! Spreadsheets store dates as serial numbers: whole days since an epoch,
+//! with the time of day as the fraction. Excel workbooks use one of two
+//! epochs, chosen per workbook (`date1904` in xlsx and xlsb, the `1904`
+//! record in xls):
+//!
+//! - **1900 system**: serial 1 is 1900-01-01. For compatibility with Lotus
+//!   1-2-3, 1900 is treated as a leap year, so serial 60 is the fictitious
+//!   1900-02-29 and every date before it is one day off from a plain day
+//!   count. Serial 0 is shown as 1900-01-00.
+//! - **1904 system**: serial 0 is 1904-01-01, without any fictitious day.
+//!
+//! [`DateSystem`] keeps these rules in one place so that readers, the
+//! formula evaluator and [`CellValue::as_datetime`](super::CellValue::as_datetime)
+//! agree on what a serial means.
+
+use chrono::{Datelike, NaiveDate, NaiveDateTime, TimeDelta, Weekday};
+
+use super::CellValue;
+
+const MILLIS_PER_DAY: i64 = 86_400_000;
+
+/// Days from the 1900 system's epoch to 1904-01-01, the 1904 system's epoch.
+const DAYS_1900_TO_1904: i64 = 1462;
+
+/// The epoch a workbook counts date serials from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DateSystem {
+    /// Serial 1 is 1900-01-01, with the fictitious 1900-02-29 as serial 60
+    #[default]
+    Excel1900,
+    /// Serial 0 is 1904-01-01
+    Excel1904,
+}
+
+impl DateSystem {
+    /// The date system of a workbook, given its `date1904` flag.
+    pub fn from_1904_flag(is_1904: bool) -> Self {
+        if is_1904 {
+            DateSystem::Excel1904
+        } else {
+            DateSystem::Excel1900
+        }
+    }
+
+    /// Whether this is the 1904 date system.
+    pub fn is_1904(self) -> bool {
+        self == DateSystem::Excel1904
+    }
+
+    /// Serial number of a date.
+    ///
+    /// In the 1900 system, dates before 1900-03-01 get the serials Excel
+    /// gives them, one less than a plain day count from 1899-12-30.
+    pub fn date_to_serial(self, date: NaiveDate) -> f64 {
+        let days = match self {
+            DateSystem::Excel1900 => {
+                let days = (date - Self::base_1900()).num_days();
+                if days < 61 { days - 1 } else { days }
+            },
+            DateSystem::Excel1904 => (date - Self::base_1904()).num_days(),
+        };
+        days as f64
+    }
+
+    /// Serial number of a date and time.
+    pub fn datetime_to_serial(self, datetime: NaiveDateTime) -> f64 {
+        let time = datetime.time() - chrono::NaiveTime::MIN;
+        self.date_to_serial(datetime.date())
+            + time.num_milliseconds() as f64 / MILLIS_PER_DAY as f64
+    }
+
+    /// Date of the day a serial falls on, ignoring the time of day.
+    ///
+    /// Returns `None` for negative serials, serials past year 9999 and, in
+    /// the 1900 system, serial 60 (1900-02-29), which has no calendar date.
+    /// Serial 0 of the 1900 system (1900-01-00) reads as 1899-12-31.
+    pub fn serial_to_date(self, serial: f64) -> Option<NaiveDate> {
+        if !serial.is_finite() || serial < 0.0 {
+            return None;
+        }
+        self.day_to_date(serial.floor() as i64)
+    }
+
+    /// Date and time of a serial, rounded to the millisecond.
+    ///
+    /// Returns `None` for the same serials as [`serial_to_date`](Self::serial_to_date).
+    pub fn serial_to_datetime(self, serial: f64) -> Option<NaiveDateTime> {
+        if !serial.is_finite() || serial < 0.0 {
+            return None;
+        }
+        let millis = (serial * MILLIS_PER_DAY as f64).round() as i64;
+        let date = self.day_to_date(millis.div_euclid(MILLIS_PER_DAY))?;
+        date.and_hms_opt(0, 0, 0)?
+            .checked_add_signed(TimeDelta::try_milliseconds(
+                millis.rem_euclid(MILLIS_PER_DAY),
+            )?)
+    }
+
+    /// Year, month and day shown for a serial.
+    ///
+    /// Unlike [`serial_to_date`](Self::serial_to_date), this gives Excel's
+    /// 1900-01-00 for serial 0 and 1900-02-29 for serial 60 of the 1900
+    /// system.
+    pub fn serial_to_ymd(self, serial: f64) -> Option<(i32, u32, u32)> {
+        if self == DateSystem::Excel1900 && (0.0..1.0).contains(&serial) {
+            return Some((1900, 1, 0));
+        }
+        if self == DateSystem::Excel1900 && (60.0..61.0).contains(&serial) {
+            return Some((1900, 2, 29));
+        }
+        let date = self.serial_to_date(serial)?;
+        Some((date.year(), date.month(), date.day()))
+    }
+
+    /// Serial of a year, month and day, where months past December and days
+    /// past the end of the month carry over like they do in Excel's `DATE`.
+    ///
+    /// In the 1900 system, February 1900 has 29 days. Returns `None` when the
+    /// result is before the epoch or after year 9999.
+    pub fn ymd_to_serial(self, year: i32, month: i64, day: i64) -> Option<f64> {
+        let months = i64::from(year) * 12 + month - 1;
+        let year = i32::try_from(months.div_euclid(12)).ok()?;
+        let month = (months.rem_euclid(12) + 1) as u32;
+        let first = NaiveDate::from_ymd_opt(year, month, 1)?;
+        let serial = self.date_to_serial(first) + (day - 1) as f64;
+        let valid = self.serial_to_date(serial).is_some()
+            || (self == DateSystem::Excel1900 && serial == 60.0);
+        valid.then_some(serial)
+    }
+
+    /// Value of a cell whose number format shows a date or time.
+    ///
+    /// Numbers that Excel shows as a date in this system become
+    /// [`CellValue::DateTime`], as does the cached number of a formula.
+    /// Other values, including negative serials, are returned unchanged.
+    pub fn date_cell_value(self, value: CellValue) -> CellValue {
+        match value {
+            CellValue::Int(n) if self.serial_to_ymd(n as f64).is_some() => {
+                CellValue::DateTime(n as f64)
+            },
+            CellValue::Float(f) if self.serial_to_ymd(f).is_some() => CellValue::DateTime(f),
+            CellValue::Formula {
+                formula,
+                cached_value,
+                is_array,
+                array_range,
+            } => CellValue::Formula {
+                formula,
+                cached_value: cached_value.map(|value| Box::new(self.date_cell_value(*value))),
+                is_array,
+                array_range,
+            },
+            other => other,
+        }
+    }
+
+    /// Day of the week of a serial.
+    ///
+    /// Serials before 1900-03-01 follow Excel, which counts weekdays from
+    /// its fictitious calendar: serial 1 (1900-01-01) is a Sunday there.
+    pub fn weekday(self, serial: f64) -> Option<Weekday> {
+        if !serial.is_finite() || serial < 0.0 {
+            return None;
+        }
+        let mut day = serial.floor() as i64;
+        if self == DateSystem::Excel1904 {
+            day += DAYS_1900_TO_1904;
+        }
+        // Serial 1 of the 1900 system is a Sunday, serial 0 a Saturday
+        Some(match (day + 6) % 7 {
+            0 => Weekday::Sun,
+            1 => Weekday::Mon,
+            2 => Weekday::Tue,
+            3 => Weekday::Wed,
+            4 => Weekday::Thu,
+            5 => Weekday::Fri,
+            _ => Weekday::Sat,
+        })
+    }
+
+    fn day_to_date(self, day: i64) -> Option<NaiveDate> {
+        let date = match self {
+            DateSystem::Excel1900 if day == 60 => return None,
+            DateSystem::Excel1900 if day < 60 => Self::base_1900() + TimeDelta::try_days(day + 1)?,
+            DateSystem::Excel1900 => {
+                Self::base_1900().checked_add_signed(TimeDelta::try_days(day)?)?
+            },
+            DateSystem::Excel1904 => {
+                Self::base_1904().checked_add_signed(TimeDelta::try_days(day)?)?
+            },
+        };
+        (date.year() <= 9999).then_some(date)
+    }
+
+    fn base_1900() -> NaiveDate {
+        NaiveDate::from_ymd_opt(1899, 12, 30).expect("valid base date")
+    }
+
+    fn base_1904() -> NaiveDate {
+        NaiveDate::from_ymd_opt(1904, 1, 1).expect("valid base date")
+    }
+}
+
+/// Check if a number format code shows numbers as dates or times.
+///
+/// Only the first section of the code counts. Durations such as
+/// `[h]:mm:ss` are not dates.
+pub(crate) fn is_date_format_code(format: &str) -> bool {
+    let mut escaped = false;
+    let mut is_quote = false;
+    let mut brackets = 0u8;
+    let mut prev = ' ';
+    let mut hms = false;
+    let mut ap = false;
+
+    for s in format.chars() {
+        match (s, escaped, is_quote, ap, brackets) {
+            (_, true, ..) => escaped = false, // if escaped, ignore
+            ('_' | '\\', ..) => escaped = true,
+            ('"', _, true, _, _) => is_quote = false,
+            (_, _, true, _, _) => (), // inside quotes, skip
+            ('"', _, _, _, _) => is_quote = true,
+            (';', ..) => return false, // first format only
+            ('[', ..) => brackets += 1,
+            (']', .., 1) if hms => return false, // TimeDelta, not DateTime
+            (']', ..) => brackets = brackets.saturating_sub(1),
+            ('a' | 'A', _, _, false, 0) => ap = true,
+            ('p' | 'm' | '/' | 'P' | 'M', _, _, true, 0) => return true,
+            ('d' | 'm' | 'h' | 'y' | 's' | 'D' | 'M' | 'H' | 'Y' | 'S', _, _, false, 0) => {
+                return true;
+            },
+            _ => {
+                if hms && s.eq_ignore_ascii_case(&prev) {
+                    // ok ...
+                } else {
+                    hms = prev == '[' && matches!(s, 'm' | 'h' | 's' | 'M' | 'H' | 'S');
+                }
+            },
+        }
+        prev = s;
+    }
+    false
+}
+
+/// Check if a number format shows a date or time, given its ID and the format
+/// code stored for it, if any.
+///
+/// Files only store the codes of custom formats. Built-in formats without a
+/// code are dates for IDs 14-22, 45 and 47, and for 27-36 and 50-58, which
+/// East Asian locales use for their date formats. ID 46 (`[h]:mm:ss`) is a
+/// duration.
+pub(crate) fn is_date_format_id(id: u32, code: Option<&str>) -> bool {
+    match code {
+        Some(code) => is_date_format_code(code),
+        None => matches!(id, 14..=22 | 27..=36 | 45 | 47 | 50..=58),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_1900_leap_year_bug() {
+        let system = DateSystem::Excel1900;
+        for (serial, expected) in [
+            (1.0, date(1900, 1, 1)),
+            (59.0, date(1900, 2, 28)),
+            (61.0, date(1900, 3, 1)),
+            (45306.0, date(2024, 1, 15)),
+            (2958465.0, date(9999, 12, 31)),
+        ] {
+            assert_eq!(system.serial_to_date(serial), Some(expected));
+            assert_eq!(system.date_to_serial(expected), serial);
+        }
+        assert_eq!(system.serial_to_date(60.0), None);
+        assert_eq!(system.serial_to_date(2958466.0), None);
+        assert_eq!(system.serial_to_ymd(60.5), Some((1900, 2, 29)));
+        assert_eq!(system.serial_to_ymd(0.0), Some((1900, 1, 0)));
+
+        assert_eq!(system.ymd_to_serial(1900, 2, 29), Some(60.0));
+        assert_eq!(system.ymd_to_serial(1900, 3, 0), Some(60.0));
+        assert_eq!(system.ymd_to_serial(2023, 14, 1), Some(45323.0));
+        assert_eq!(system.ymd_to_serial(1899, 12, 30), None);
+
+        assert_eq!(system.weekday(1.0), Some(Weekday::Sun));
+        assert_eq!(system.weekday(60.0), Some(Weekday::Wed));
+        assert_eq!(system.weekday(61.0), Some(Weekday::Thu));
+    }
+
+    #[test]
+    fn test_is_date_format_id() {
+        assert!(is_date_format_id(14, None));
+        assert!(is_date_format_id(31, None));
+        assert!(!is_date_format_id(46, None));
+        assert!(!is_date_format_id(2, None));
+        assert!(is_date_format_id(164, Some("yyyy-mm-dd")));
+        assert!(!is_date_format_id(164, Some("0.00 \"USD\"")));
+    }
+
+    #[test]
+    fn test_date_cell_value() {
+        let system = DateSystem::Excel1900;
+        assert_eq!(
+            system.date_cell_value(CellValue::Int(45306)),
+            CellValue::DateTime(45306.0)
+        );
+        assert_eq!(
+            system.date_cell_value(CellValue::Float(60.5)),
+            CellValue::DateTime(60.5)
+        );
+        assert_eq!(
+            system.date_cell_value(CellValue::Float(-1.0)),
+            CellValue::Float(-1.0)
+        );
+        assert_eq!(
+            system.date_cell_value(CellValue::String("45306".into())),
+            CellValue::String("45306".into())
+        );
+        assert_eq!(
+            system.date_cell_value(CellValue::Formula {
+                formula: "TODAY()".into(),
+                cached_value: Some(Box::new(CellValue::Int(45306))),
+                is_array: false,
+                array_range: None,
+            }),
+            CellValue::Formula {
+                formula: "TODAY()".into(),
+                cached_value: Some(Box::new(CellValue::DateTime(45306.0))),
+                is_array: false,
+                array_range: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_1904_system() {
+        let system = DateSystem::Excel1904;
+        assert_eq!(system.serial_to_date(0.0), Some(date(1904, 1, 1)));
+        assert_eq!(system.date_to_serial(date(2024, 1, 15)), 43844.0);
+        assert_eq!(system.ymd_to_serial(1904, 2, 29), Some(59.0));
+        assert_eq!(system.weekday(0.0), Some(Weekday::Fri));
+        assert_eq!(
+            system.serial_to_datetime(43844.75),
+            date(2024, 1, 15).and_hms_opt(18, 0, 0)
+        );
+        assert_eq!(
+            system.datetime_to_serial(date(2024, 1, 15).and_hms_opt(18, 0, 0).unwrap()),
+            43844.75
+        );
+    }
+}
@@ -0,0 +1,766 @@
+//! Formula dependency graph for auditing workbooks.
+//!
+//! [`DependencyGraph`] parses every formula of a workbook with the evaluator's
+//! expression parser and records the cells, ranges and names each formula
+//! reads. It answers "which cells does this cell depend on" and "which cells
+//! depend on this cell" across sheets, gives the order in which formulas have
+//! to be calculated and reports circular references.
+//!
+//! References computed at calculation time (`OFFSET`, `INDIRECT`) cannot be
+//! known without evaluating the workbook; they are reported as
+//! [`Precedent::Dynamic`] so that audits can flag them.
+
+use super::parser::reference::{column_index_to_letters, quote_sheet_name};
+use super::parser::{Expr, RangeRef, parse_expression};
+use crate::sheet::{CellValue, Result, WorkbookTrait};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fmt::Write as _;
+
+/// Functions whose result can change without any of their precedents
+/// changing, so Excel recalculates them on every change.
+const VOLATILE_FUNCTIONS: &[&str] = &[
+    "NOW",
+    "TODAY",
+    "RAND",
+    "RANDBETWEEN",
+    "RANDARRAY",
+    "OFFSET",
+    "INDIRECT",
+    "INFO",
+    "CELL",
+];
+
+/// Functions that compute the cells they read at calculation time.
+const DYNAMIC_FUNCTIONS: &[&str] = &["OFFSET", "INDIRECT"];
+
+/// Names nested deeper than this are left unresolved, which also stops
+/// names that refer to themselves.
+const MAX_NAME_DEPTH: usize = 16;
+
+/// A cell of a workbook. Row and column are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CellId {
+    pub sheet: String,
+    pub row: u32,
+    pub col: u32,
+}
+
+impl CellId {
+    /// Create a cell id.
+    pub fn new(sheet: impl Into<String>, row: u32, col: u32) -> Self {
+        Self {
+            sheet: sheet.into(),
+            row,
+            col,
+        }
+    }
+}
+
+impl fmt::Display for CellId {
+    /// Formats the cell as a sheet-qualified A1 reference, e.g. `Sheet1!B2`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}!{}{}",
+            quote_sheet_name(&self.sheet),
+            column_index_to_letters(self.col),
+            self.row
+        )
+    }
+}
+
+/// Something a formula reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Precedent {
+    /// A single cell
+    Cell(CellId),
+    /// A rectangular range of cells
+    Range(RangeRef),
+    /// A name that is not defined, such as a structured table reference
+    Name(String),
+    /// Cells computed at calculation time by the given function (`OFFSET` or
+    /// `INDIRECT`)
+    Dynamic(String),
+}
+
+impl Precedent {
+    /// Whether the precedent covers the given cell.
+    ///
+    /// Names and dynamic references never do, as the cells they read are
+    /// not known.
+    pub fn contains(&self, sheet: &str, row: u32, col: u32) -> bool {
+        match self {
+            Precedent::Cell(cell) => cell.sheet == sheet && cell.row == row && cell.col == col,
+            Precedent::Range(range) => {
+                range.sheet == sheet
+                    && (range.start_row.min(range.end_row)..=range.start_row.max(range.end_row))
+                        .contains(&row)
+                    && (range.start_col.min(range.end_col)..=range.start_col.max(range.end_col))
+                        .contains(&col)
+            },
+            Precedent::Name(_) | Precedent::Dynamic(_) => false,
+        }
+    }
+}
+
+impl fmt::Display for Precedent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Precedent::Cell(cell) => fmt::Display::fmt(cell, f),
+            Precedent::Range(range) => write!(
+                f,
+                "{}!{}{}:{}{}",
+                quote_sheet_name(&range.sheet),
+                column_index_to_letters(range.start_col),
+                range.start_row,
+                column_index_to_letters(range.end_col),
+                range.end_row
+            ),
+            Precedent::Name(name) => f.write_str(name),
+            Precedent::Dynamic(function) => write!(f, "{}()", function),
+        }
+    }
+}
+
+/// A formula cell and what it reads.
+#[derive(Debug, Clone)]
+pub struct FormulaCell {
+    /// Position of the formula
+    pub cell: CellId,
+    /// Formula text, without the leading `=`
+    pub formula: String,
+    /// Cells, ranges and names read by the formula, with defined names
+    /// replaced by what they refer to
+    pub precedents: Vec<Precedent>,
+    /// Volatile functions called by the formula, in upper case
+    pub volatile_functions: Vec<String>,
+    /// Whether the formula could be parsed. Formulas that could not be
+    /// parsed have no precedents.
+    pub parsed: bool,
+}
+
+impl FormulaCell {
+    /// Whether the formula calls a volatile function.
+    pub fn is_volatile(&self) -> bool {
+        !self.volatile_functions.is_empty()
+    }
+
+    /// Whether the formula reads cells that are only known at calculation
+    /// time.
+    pub fn has_dynamic_precedents(&self) -> bool {
+        self.precedents
+            .iter()
+            .any(|precedent| matches!(precedent, Precedent::Dynamic(_)))
+    }
+}
+
+/// Formulas that depend on each other, returned by
+/// [`DependencyGraph::topological_order`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CircularReference {
+    /// The cells of each cycle, in workbook order
+    pub cycles: Vec<Vec<CellId>>,
+}
+
+impl fmt::Display for CircularReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("circular reference")?;
+        for (i, cycle) in self.cycles.iter().enumerate() {
+            f.write_str(if i == 0 { ": " } else { "; " })?;
+            for (j, cell) in cycle.iter().enumerate() {
+                if j > 0 {
+                    f.write_str(", ")?;
+                }
+                fmt::Display::fmt(cell, f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CircularReference {}
+
+/// Dependencies between the formulas of a workbook.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use litchi::ooxml::xlsx::Workbook;
+/// use litchi::sheet::DependencyGraph;
+///
+/// let workbook = Workbook::open("model.xlsx")?;
+/// let graph = DependencyGraph::builder(&workbook)
+///     .define_name("TaxRate", "Inputs!$B$2")
+///     .build()?;
+///
+/// for cell in graph.dependents_of("Inputs", 2, 2) {
+///     println!("{} uses the tax rate", cell);
+/// }
+/// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DependencyGraph {
+    formulas: Vec<FormulaCell>,
+    index: HashMap<CellId, usize>,
+    /// Formulas read by each formula, directly or through a range
+    precedent_formulas: Vec<Vec<usize>>,
+    /// Formulas reading each formula, the reverse of `precedent_formulas`
+    dependent_formulas: Vec<Vec<usize>>,
+}
+
+impl DependencyGraph {
+    /// Build the graph of a workbook without defined names.
+    ///
+    /// Use [`builder`](Self::builder) to resolve defined names, which
+    /// workbooks do not expose through [`WorkbookTrait`].
+    pub fn build<W: WorkbookTrait + ?Sized>(workbook: &W) -> Result<Self> {
+        Self::builder(workbook).build()
+    }
+
+    /// Start building the graph of a workbook.
+    pub fn builder<W: WorkbookTrait + ?Sized>(workbook: &W) -> DependencyGraphBuilder<'_, W> {
+        DependencyGraphBuilder {
+            workbook,
+            names: HashMap::new(),
+            local_names: HashMap::new(),
+        }
+    }
+
+    /// All formula cells, by sheet, then row, then column.
+    pub fn formulas(&self) -> &[FormulaCell] {
+        &self.formulas
+    }
+
+    /// The formula at the given cell, if any.
+    pub fn formula(&self, sheet: &str, row: u32, col: u32) -> Option<&FormulaCell> {
+        self.index
+            .get(&CellId::new(sheet, row, col))
+            .map(|&i| &self.formulas[i])
+    }
+
+    /// What the formula at the given cell reads. Empty for cells without a
+    /// formula.
+    pub fn precedents_of(&self, sheet: &str, row: u32, col: u32) -> &[Precedent] {
+        self.formula(sheet, row, col)
+            .map_or(&[], |formula| &formula.precedents)
+    }
+
+    /// Formula cells that read the given cell directly, through a range or
+    /// through a defined name.
+    pub fn dependents_of(&self, sheet: &str, row: u32, col: u32) -> Vec<&CellId> {
+        self.direct_dependents(sheet, row, col)
+            .map(|i| &self.formulas[i].cell)
+            .collect()
+    }
+
+    /// Formula cells whose result depends on the given cell, directly or
+    /// through other formulas, in workbook order.
+    pub fn transitive_dependents_of(&self, sheet: &str, row: u32, col: u32) -> Vec<&CellId> {
+        let mut seen = vec![false; self.formulas.len()];
+        let mut stack: Vec<usize> = self.direct_dependents(sheet, row, col).collect();
+        while let Some(i) = stack.pop() {
+            if !std::mem::replace(&mut seen[i], true) {
+                stack.extend(&self.dependent_formulas[i]);
+            }
+        }
+        (0..self.formulas.len())
+            .filter(|&i| seen[i])
+            .map(|i| &self.formulas[i].cell)
+            .collect()
+    }
+
+    /// Formulas calling a volatile function.
+    pub fn volatile_cells(&self) -> impl Iterator<Item = &FormulaCell> {
+        self.formulas.iter().filter(|formula| formula.is_volatile())
+    }
+
+    /// Formulas reading cells that are only known at calculation time.
+    pub fn dynamic_cells(&self) -> impl Iterator<Item = &FormulaCell> {
+        self.formulas
+            .iter()
+            .filter(|formula| formula.has_dynamic_precedents())
+    }
+
+    /// Formulas that could not be parsed, and whose precedents are therefore
+    /// unknown.
+    pub fn unparsed_cells(&self) -> impl Iterator<Item = &FormulaCell> {
+        self.formulas.iter().filter(|formula| !formula.parsed)
+    }
+
+    /// Formula cells ordered so that every formula comes after the formulas
+    /// it reads.
+    ///
+    /// Fails with the cells of every cycle when formulas depend on each
+    /// other.
+    pub fn topological_order(&self) -> std::result::Result<Vec<&CellId>, CircularReference> {
+        let components = self.strongly_connected_components();
+        let mut cycles: Vec<Vec<usize>> = components
+            .iter()
+            .filter(|component| {
+                component.len() > 1 || self.precedent_formulas[component[0]].contains(&component[0])
+            })
+            .map(|component| {
+                let mut component = component.clone();
+                component.sort_unstable();
+                component
+            })
+            .collect();
+        if !cycles.is_empty() {
+            cycles.sort_unstable();
+            let cycles = cycles
+                .into_iter()
+                .map(|cycle| {
+                    cycle
+                        .into_iter()
+                        .map(|i| self.formulas[i].cell.clone())
+                        .collect()
+                })
+                .collect();
+            return Err(CircularReference { cycles });
+        }
+        Ok(components
+            .into_iter()
+            .flatten()
+            .map(|i| &self.formulas[i].cell)
+            .collect())
+    }
+
+    /// Render the graph in Graphviz DOT format.
+    ///
+    /// Edges point from precedents to the formulas reading them. Volatile
+    /// formulas are filled, and dynamic references are drawn dashed.
+    pub fn to_dot(&self) -> String {
+        fn quote(id: &str) -> String {
+            format!("\"{}\"", id.replace('\\', "\\\\").replace('"', "\\\""))
+        }
+
+        let mut dot = String::from("digraph dependencies {\n");
+        for formula in &self.formulas {
+            let id = quote(&formula.cell.to_string());
+            if formula.is_volatile() {
+                let _ = writeln!(dot, "    {} [style=filled, fillcolor=lightyellow];", id);
+            } else {
+                let _ = writeln!(dot, "    {};", id);
+            }
+        }
+        for formula in &self.formulas {
+            let id = quote(&formula.cell.to_string());
+            for precedent in &formula.precedents {
+                let style = match precedent {
+                    Precedent::Dynamic(_) => " [style=dashed]",
+                    _ => "",
+                };
+                let _ = writeln!(
+                    dot,
+                    "    {} -> {}{};",
+                    quote(&precedent.to_string()),
+                    id,
+                    style
+                );
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Indices of the formulas reading the given cell.
+    fn direct_dependents<'a>(
+        &'a self,
+        sheet: &'a str,
+        row: u32,
+        col: u32,
+    ) -> impl Iterator<Item = usize> + 'a {
+        self.formulas
+            .iter()
+            .enumerate()
+            .filter(move |(_, formula)| {
+                formula
+                    .precedents
+                    .iter()
+                    .any(|precedent| precedent.contains(sheet, row, col))
+            })
+            .map(|(i, _)| i)
+    }
+
+    /// Link each formula to the formulas it reads.
+    fn link(&mut self) {
+        let mut by_sheet: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, formula) in self.formulas.iter().enumerate() {
+            by_sheet.entry(&formula.cell.sheet).or_default().push(i);
+        }
+
+        let mut precedent_formulas = vec![Vec::new(); self.formulas.len()];
+        for (i, formula) in self.formulas.iter().enumerate() {
+            let mut linked = HashSet::new();
+            for precedent in &formula.precedents {
+                match precedent {
+                    Precedent::Cell(cell) => linked.extend(self.index.get(cell).copied()),
+                    Precedent::Range(range) => {
+                        let Some(candidates) = by_sheet.get(range.sheet.as_str()) else {
+                            continue;
+                        };
+                        let rows = range.start_row.abs_diff(range.end_row) as u64 + 1;
+                        let cols = range.start_col.abs_diff(range.end_col) as u64 + 1;
+                        if rows * cols < candidates.len() as u64 {
+                            let (first_row, first_col) = (
+                                range.start_row.min(range.end_row),
+                                range.start_col.min(range.end_col),
+                            );
+                            for row in first_row..first_row + rows as u32 {
+                                for col in first_col..first_col + cols as u32 {
+                                    let cell = CellId::new(range.sheet.as_str(), row, col);
+                                    linked.extend(self.index.get(&cell).copied());
+                                }
+                            }
+                        } else {
+                            linked.extend(candidates.iter().copied().filter(|&j| {
+                                let cell = &self.formulas[j].cell;
+                                precedent.contains(&cell.sheet, cell.row, cell.col)
+                            }));
+                        }
+                    },
+                    Precedent::Name(_) | Precedent::Dynamic(_) => {},
+                }
+            }
+            let mut linked: Vec<usize> = linked.into_iter().collect();
+            linked.sort_unstable();
+            precedent_formulas[i] = linked;
+        }
+
+        let mut dependent_formulas = vec![Vec::new(); self.formulas.len()];
+        for (i, precedents) in precedent_formulas.iter().enumerate() {
+            for &j in precedents {
+                dependent_formulas[j].push(i);
+            }
+        }
+        self.precedent_formulas = precedent_formulas;
+        self.dependent_formulas = dependent_formulas;
+    }
+
+    /// Tarjan's algorithm, without recursion so that long chains of formulas
+    /// cannot overflow the stack.
+    ///
+    /// Components come out after every component they read, which is the
+    /// calculation order.
+    fn strongly_connected_components(&self) -> Vec<Vec<usize>> {
+        const UNVISITED: usize = usize::MAX;
+
+        let n = self.formulas.len();
+        let mut order = vec![UNVISITED; n];
+        let mut low = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut stack = Vec::new();
+        let mut next = 0;
+        let mut components = Vec::new();
+
+        for root in 0..n {
+            if order[root] != UNVISITED {
+                continue;
+            }
+            let mut work = vec![(root, 0)];
+            while let Some(&(v, child)) = work.last() {
+                if order[v] == UNVISITED {
+                    order[v] = next;
+                    low[v] = next;
+                    next += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                }
+
+                if let Some(&w) = self.precedent_formulas[v].get(child) {
+                    let top = work.len() - 1;
+                    work[top].1 += 1;
+                    if order[w] == UNVISITED {
+                        work.push((w, 0));
+                    } else if on_stack[w] {
+                        low[v] = low[v].min(order[w]);
+                    }
+                    continue;
+                }
+
+                work.pop();
+                if let Some(&(parent, _)) = work.last() {
+                    low[parent] = low[parent].min(low[v]);
+                }
+                if low[v] == order[v] {
+                    let mut component = Vec::new();
+                    while let Some(w) = stack.pop() {
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    components.push(component);
+                }
+            }
+        }
+        components
+    }
+}
+
+/// Builder for a [`DependencyGraph`], created by
+/// [`DependencyGraph::builder`].
+///
+/// Defined names are resolved like [`FormulaEvaluator`](super::FormulaEvaluator)
+/// does: sheet-local names first, then workbook names. A name may refer to a
+/// reference or to a formula.
+pub struct DependencyGraphBuilder<'a, W: WorkbookTrait + ?Sized> {
+    workbook: &'a W,
+    names: HashMap<String, String>,
+    local_names: HashMap<(String, String), String>,
+}
+
+impl<'a, W: WorkbookTrait + ?Sized> DependencyGraphBuilder<'a, W> {
+    /// Define a workbook name.
+    pub fn define_name(&mut self, name: &str, reference: &str) -> &mut Self {
+        self.names
+            .insert(name.trim().to_uppercase(), reference.trim().to_string());
+        self
+    }
+
+    /// Define a name visible only to formulas on the given sheet.
+    pub fn define_name_local(
+        &mut self,
+        sheet_name: &str,
+        name: &str,
+        reference: &str,
+    ) -> &mut Self {
+        self.local_names.insert(
+            (sheet_name.to_string(), name.trim().to_uppercase()),
+            reference.trim().to_string(),
+        );
+        self
+    }
+
+    /// Read every formula of the workbook and link them.
+    pub fn build(&self) -> Result<DependencyGraph> {
+        let mut graph = DependencyGraph::default();
+        for (index, sheet_name) in self.workbook.worksheet_names().iter().enumerate() {
+            let sheet = self.workbook.worksheet_by_index(index)?;
+            let mut formulas = Vec::new();
+            let mut cells = sheet.cells();
+            while let Some(cell) = cells.next() {
+                let cell = cell?;
+                if let CellValue::Formula { formula, .. } = cell.value() {
+                    formulas.push((cell.row(), cell.column(), formula.clone()));
+                }
+            }
+            formulas.sort_unstable_by_key(|&(row, col, _)| (row, col));
+
+            for (row, col, formula) in formulas {
+                let formula = formula.trim();
+                let formula = formula.strip_prefix('=').unwrap_or(formula);
+                let id = CellId::new(sheet_name.as_str(), row, col);
+                graph.index.insert(id.clone(), graph.formulas.len());
+                graph.formulas.push(self.read_formula(id, formula));
+            }
+        }
+        graph.link();
+        Ok(graph)
+    }
+
+    fn read_formula(&self, cell: CellId, formula: &str) -> FormulaCell {
+        let mut collector = Collector {
+            builder: self,
+            precedents: Vec::new(),
+            volatile_functions: Vec::new(),
+        };
+        let parsed = match parse_expression(&cell.sheet, formula) {
+            Some(expr) => {
+                collector.collect(&cell.sheet, &expr, 0);
+                true
+            },
+            None => false,
+        };
+        FormulaCell {
+            cell,
+            formula: formula.to_string(),
+            precedents: collector.precedents,
+            volatile_functions: collector.volatile_functions,
+            parsed,
+        }
+    }
+
+    fn resolve_name(&self, sheet: &str, name: &str) -> Option<&str> {
+        let key = name.trim().to_uppercase();
+        self.local_names
+            .get(&(sheet.to_string(), key.clone()))
+            .or_else(|| self.names.get(&key))
+            .map(String::as_str)
+    }
+}
+
+/// Walks a parsed formula, collecting what it reads.
+struct Collector<'b, 'a, W: WorkbookTrait + ?Sized> {
+    builder: &'b DependencyGraphBuilder<'a, W>,
+    precedents: Vec<Precedent>,
+    volatile_functions: Vec<String>,
+}
+
+impl<W: WorkbookTrait + ?Sized> Collector<'_, '_, W> {
+    fn collect(&mut self, sheet: &str, expr: &Expr, depth: usize) {
+        match expr {
+            Expr::Literal(_) => {},
+            Expr::Reference { sheet, row, col } => {
+                self.push(Precedent::Cell(CellId::new(sheet.as_str(), *row, *col)))
+            },
+            Expr::Range(range) => self.push(Precedent::Range(range.clone())),
+            Expr::Name(name) => self.collect_name(sheet, name, depth),
+            Expr::UnaryMinus(inner) => self.collect(sheet, inner, depth),
+            Expr::Binary { left, right, .. } => {
+                self.collect(sheet, left, depth);
+                self.collect(sheet, right, depth);
+            },
+            Expr::FunctionCall { name, args } => {
+                let name = name.strip_prefix("_XLFN.").unwrap_or(name);
+                if VOLATILE_FUNCTIONS.contains(&name)
+                    && !self.volatile_functions.iter().any(|f| f == name)
+                {
+                    self.volatile_functions.push(name.to_string());
+                }
+                if DYNAMIC_FUNCTIONS.contains(&name) {
+                    self.push(Precedent::Dynamic(name.to_string()));
+                }
+                for arg in args {
+                    self.collect(sheet, arg, depth);
+                }
+            },
+        }
+    }
+
+    /// Replace a defined name by what it refers to, evaluated on `sheet`.
+    fn collect_name(&mut self, sheet: &str, name: &str, depth: usize) {
+        let expr = self
+            .builder
+            .resolve_name(sheet, name)
+            .filter(|_| depth < MAX_NAME_DEPTH)
+            .and_then(|reference| {
+                parse_expression(sheet, reference.strip_prefix('=').unwrap_or(reference))
+            });
+        match expr {
+            Some(expr) => self.collect(sheet, &expr, depth + 1),
+            None => self.push(Precedent::Name(name.to_string())),
+        }
+    }
+
+    fn push(&mut self, precedent: Precedent) {
+        if !self.precedents.contains(&precedent) {
+            self.precedents.push(precedent);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "ooxml"))]
+mod tests {
+    use super::*;
+    use crate::ooxml::xlsx::Workbook as XlsxWorkbook;
+    use tempfile::tempdir;
+
+    fn open(formulas: &[(&str, u32, u32, &str)]) -> XlsxWorkbook {
+        let mut wb = XlsxWorkbook::create().unwrap();
+        wb.worksheet_mut(0).unwrap().set_name("Inputs".to_string());
+        wb.add_worksheet("Model Sheet");
+        for &(sheet, row, col, formula) in formulas {
+            let index = if sheet == "Inputs" { 0 } else { 1 };
+            wb.worksheet_mut(index)
+                .unwrap()
+                .set_cell_formula(row, col, formula);
+        }
+        wb.worksheet_mut(0).unwrap().set_cell_value(1, 1, 2);
+
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("dependencies.xlsx");
+        wb.save(&path).unwrap();
+        XlsxWorkbook::open(&path).unwrap()
+    }
+
+    #[test]
+    fn test_precedents_and_dependents() {
+        let wb = open(&[
+            ("Inputs", 2, 1, "A1*TaxRate"),
+            ("Model Sheet", 1, 1, "SUM(Inputs!A1:A2)+Inputs!B1"),
+            ("Model Sheet", 2, 1, "A1+OFFSET(A1,1,0)+NOW()"),
+            ("Model Sheet", 3, 1, "Table1[Amount]"),
+        ]);
+        let graph = DependencyGraph::builder(&wb)
+            .define_name("TaxRate", "Inputs!$C$1")
+            .build()
+            .unwrap();
+        assert_eq!(graph.formulas().len(), 4);
+
+        assert_eq!(
+            graph.precedents_of("Inputs", 2, 1),
+            [
+                Precedent::Cell(CellId::new("Inputs", 1, 1)),
+                Precedent::Cell(CellId::new("Inputs", 1, 3)),
+            ]
+        );
+        let precedents: Vec<String> = graph
+            .precedents_of("Model Sheet", 1, 1)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(precedents, ["Inputs!A1:A2", "Inputs!B1"]);
+        assert!(graph.precedents_of("Inputs", 1, 1).is_empty());
+
+        // The input is read through a range and, transitively, by Model!A2
+        let direct: Vec<String> = graph
+            .dependents_of("Inputs", 1, 1)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(direct, ["Inputs!A2", "'Model Sheet'!A1"]);
+        assert_eq!(graph.transitive_dependents_of("Inputs", 1, 3).len(), 3);
+
+        let volatile: Vec<_> = graph
+            .volatile_cells()
+            .map(|f| &f.volatile_functions)
+            .collect();
+        assert_eq!(volatile, [&["OFFSET".to_string(), "NOW".to_string()]]);
+        assert_eq!(graph.dynamic_cells().count(), 1);
+        assert_eq!(
+            graph.precedents_of("Model Sheet", 3, 1),
+            [Precedent::Name("Table1[Amount]".to_string())]
+        );
+
+        let order: Vec<String> = graph
+            .topological_order()
+            .unwrap()
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        let position = |cell: &str| order.iter().position(|c| c == cell).unwrap();
+        assert!(position("Inputs!A2") < position("'Model Sheet'!A1"));
+        assert!(position("'Model Sheet'!A1") < position("'Model Sheet'!A2"));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph dependencies {"));
+        assert!(dot.contains("\"Inputs!A1:A2\" -> \"'Model Sheet'!A1\";"));
+        assert!(dot.contains("\"OFFSET()\" -> \"'Model Sheet'!A2\" [style=dashed];"));
+    }
+
+    #[test]
+    fn test_circular_references() {
+        let wb = open(&[
+            ("Inputs", 2, 1, "B2+1"),
+            ("Inputs", 2, 2, "SUM(A2:A3)"),
+            ("Inputs", 3, 3, "C3"),
+            ("Inputs", 4, 1, "A2"),
+        ]);
+        let graph = DependencyGraph::build(&wb).unwrap();
+        let err = graph.topological_order().unwrap_err();
+        assert_eq!(
+            err.cycles,
+            [
+                vec![CellId::new("Inputs", 2, 1), CellId::new("Inputs", 2, 2)],
+                vec![CellId::new("Inputs", 3, 3)],
+            ]
+        );
+        assert_eq!(
+            err.to_string(),
+            "circular reference: Inputs!A2, Inputs!B2; Inputs!C3"
+        );
+    }
+}
@@ -52,6 +52,7 @@ pub(crate) mod test_helpers {
     use crate::sheet::Result;
     use crate::sheet::eval::BoxFuture;
     use std::collections::HashMap;
+    use std::sync::atomic::{AtomicBool, Ordering};
     use std::sync::{Arc, RwLock};
 
     /// A simple test engine for creating EvalCtx in tests.
@@ -59,6 +60,7 @@ pub(crate) mod test_helpers {
         cells: Arc<RwLock<HashMap<(String, u32, u32), CellValue>>>,
         current_pos: Arc<RwLock<Option<(String, u32, u32)>>>,
         sheet_count: usize,
+        is_1904: AtomicBool,
     }
 
     impl TestEngine {
@@ -67,6 +69,7 @@ pub(crate) mod test_helpers {
                 cells: Arc::new(RwLock::new(HashMap::new())),
                 current_pos: Arc::new(RwLock::new(None)),
                 sheet_count: 1,
+                is_1904: AtomicBool::new(false),
             }
         }
 
@@ -113,6 +116,11 @@ pub(crate) mod test_helpers {
             let mut pos = self.current_pos.write().unwrap();
             *pos = Some((sheet.to_string(), row, col));
         }
+
+        /// Switch the engine to the 1904 date system
+        pub(crate) fn set_1904_date_system(&self, is_1904: bool) {
+            self.is_1904.store(is_1904, Ordering::Relaxed);
+        }
     }
 
     impl EngineCtx for TestEngine {
@@ -147,7 +155,7 @@ pub(crate) mod test_helpers {
         }
 
         fn is_1904_date_system(&self) -> bool {
-            false
+            self.is_1904.load(Ordering::Relaxed)
         }
 
         #[cfg(feature = "eval_engine_web_functions")]
@@ -2,9 +2,14 @@ use crate::sheet::eval::engine::{EvalCtx, evaluate_expression};
 use crate::sheet::eval::parser::Expr;
 use crate::sheet::{CellValue, Result};
 
-use chrono::Datelike;
+use super::helpers::{coerce_date_serial, coerce_time_fraction, date_system, time_parts};
 
-use super::helpers::{SECONDS_PER_DAY, coerce_date_value, coerce_time_fraction};
+/// Year, month and day Excel shows for a date argument, including the
+/// 1900-01-00 and 1900-02-29 of the 1900 date system.
+fn date_parts(ctx: EvalCtx<'_>, value: &CellValue) -> Option<(i32, u32, u32)> {
+    let system = date_system(ctx);
+    system.serial_to_ymd(coerce_date_serial(system, value)?)
+}
 
 pub(crate) async fn eval_year(
     ctx: EvalCtx<'_>,
@@ -15,8 +20,8 @@ pub(crate) async fn eval_year(
         return Ok(CellValue::Error("YEAR expects 1 argument".to_string()));
     }
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
-    match coerce_date_value(&value) {
-        Some(date) => Ok(CellValue::Int(date.year() as i64)),
+    match date_parts(ctx, &value) {
+        Some((year, _, _)) => Ok(CellValue::Int(year as i64)),
         None => Ok(CellValue::Error(
             "YEAR expects a valid date serial or text".to_string(),
         )),
@@ -32,8 +37,8 @@ pub(crate) async fn eval_month(
         return Ok(CellValue::Error("MONTH expects 1 argument".to_string()));
     }
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
-    match coerce_date_value(&value) {
-        Some(date) => Ok(CellValue::Int(date.month() as i64)),
+    match date_parts(ctx, &value) {
+        Some((_, month, _)) => Ok(CellValue::Int(month as i64)),
         None => Ok(CellValue::Error(
             "MONTH expects a valid date serial or text".to_string(),
         )),
@@ -49,8 +54,8 @@ pub(crate) async fn eval_day(
         return Ok(CellValue::Error("DAY expects 1 argument".to_string()));
     }
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
-    match coerce_date_value(&value) {
-        Some(date) => Ok(CellValue::Int(date.day() as i64)),
+    match date_parts(ctx, &value) {
+        Some((_, _, day)) => Ok(CellValue::Int(day as i64)),
         None => Ok(CellValue::Error(
             "DAY expects a valid date serial or text".to_string(),
         )),
@@ -67,10 +72,7 @@ pub(crate) async fn eval_hour(
     }
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     match coerce_time_fraction(&value) {
-        Some(frac) => {
-            let seconds = (frac * SECONDS_PER_DAY).rem_euclid(SECONDS_PER_DAY);
-            Ok(CellValue::Int((seconds / 3600.0).floor() as i64))
-        },
+        Some(frac) => Ok(CellValue::Int(time_parts(frac).0)),
         None => Ok(CellValue::Error(
             "HOUR expects a valid time serial or text".to_string(),
         )),
@@ -87,11 +89,7 @@ pub(crate) async fn eval_minute(
     }
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     match coerce_time_fraction(&value) {
-        Some(frac) => {
-            let seconds = (frac * SECONDS_PER_DAY).rem_euclid(SECONDS_PER_DAY);
-            let minutes = (seconds / 60.0).floor() as i64 % 60;
-            Ok(CellValue::Int(minutes))
-        },
+        Some(frac) => Ok(CellValue::Int(time_parts(frac).1)),
         None => Ok(CellValue::Error(
             "MINUTE expects a valid time serial or text".to_string(),
         )),
@@ -108,10 +106,7 @@ pub(crate) async fn eval_second(
     }
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     match coerce_time_fraction(&value) {
-        Some(frac) => {
-            let seconds = (frac * SECONDS_PER_DAY).rem_euclid(SECONDS_PER_DAY);
-            Ok(CellValue::Int((seconds % 60.0).round() as i64))
-        },
+        Some(frac) => Ok(CellValue::Int(time_parts(frac).2)),
         None => Ok(CellValue::Error(
             "SECOND expects a valid time serial or text".to_string(),
         )),
@@ -2,11 +2,9 @@ use crate::sheet::eval::engine::{EvalCtx, evaluate_expression, to_number};
 use crate::sheet::eval::parser::Expr;
 use crate::sheet::{CellValue, Result};
 
-use chrono::Timelike;
-
 use super::helpers::{
-    SECONDS_PER_DAY, date_to_excel_serial_1900, make_date_serial_1900, number_arg,
-    parse_date_string, parse_time_string,
+    SECONDS_PER_DAY, coerce_date_serial, date_system, make_date_serial, number_arg,
+    parse_datetime_string, parse_time_string, time_fraction,
 };
 
 pub(crate) async fn eval_date(
@@ -39,7 +37,7 @@ pub(crate) async fn eval_date(
         },
     };
 
-    let serial = match make_date_serial_1900(y, m, d) {
+    let serial = match make_date_serial(date_system(ctx), y, m, d) {
         Some(s) => s,
         None => {
             return Ok(CellValue::Error("DATE arguments out of range".to_string()));
@@ -106,24 +104,20 @@ pub(crate) async fn eval_datevalue(
         return Ok(CellValue::DateTime(n.floor()));
     }
 
-    let s = match v {
-        CellValue::String(ref s) => s.trim(),
-        _ => {
-            return Ok(CellValue::Error(
-                "DATEVALUE expects a date text or serial number".to_string(),
-            ));
-        },
-    };
+    if !matches!(v, CellValue::String(_)) {
+        return Ok(CellValue::Error(
+            "DATEVALUE expects a date text or serial number".to_string(),
+        ));
+    }
 
-    let date = match parse_date_string(s) {
-        Some(d) => d,
+    let serial = match coerce_date_serial(date_system(ctx), &v) {
+        Some(serial) => serial,
         None => {
             return Ok(CellValue::Error(
                 "DATEVALUE: unsupported date format".to_string(),
             ));
         },
     };
-    let serial = date_to_excel_serial_1900(date);
     Ok(CellValue::DateTime(serial))
 }
 
@@ -153,7 +147,10 @@ pub(crate) async fn eval_timevalue(
         },
     };
 
-    let time = match parse_time_string(s) {
+    let time = match parse_datetime_string(s)
+        .map(|dt| dt.time())
+        .or_else(|| parse_time_string(s))
+    {
         Some(t) => t,
         None => {
             return Ok(CellValue::Error(
@@ -162,9 +159,7 @@ pub(crate) async fn eval_timevalue(
         },
     };
 
-    let total_seconds = time.num_seconds_from_midnight() as f64;
-    let fraction = total_seconds / SECONDS_PER_DAY;
-    Ok(CellValue::DateTime(fraction))
+    Ok(CellValue::DateTime(time_fraction(time)))
 }
 
 #[cfg(test)]
@@ -4,10 +4,10 @@ use crate::sheet::{CellValue, Result};
 
 use chrono::Utc;
 
-use super::helpers::{date_to_excel_serial_1900, datetime_to_excel_serial_1900};
+use super::helpers::date_system;
 
 pub(crate) async fn eval_today(
-    _ctx: EvalCtx<'_>,
+    ctx: EvalCtx<'_>,
     _current_sheet: &str,
     args: &[Expr],
 ) -> Result<CellValue> {
@@ -16,12 +16,12 @@ pub(crate) async fn eval_today(
     }
 
     let now = Utc::now().date_naive();
-    let serial = date_to_excel_serial_1900(now);
+    let serial = date_system(ctx).date_to_serial(now);
     Ok(CellValue::DateTime(serial))
 }
 
 pub(crate) async fn eval_now(
-    _ctx: EvalCtx<'_>,
+    ctx: EvalCtx<'_>,
     _current_sheet: &str,
     args: &[Expr],
 ) -> Result<CellValue> {
@@ -30,7 +30,7 @@ pub(crate) async fn eval_now(
     }
 
     let now = Utc::now().naive_utc();
-    let serial = datetime_to_excel_serial_1900(now);
+    let serial = date_system(ctx).datetime_to_serial(now);
     Ok(CellValue::DateTime(serial))
 }
 
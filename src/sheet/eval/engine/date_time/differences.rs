@@ -4,7 +4,7 @@ use crate::sheet::{CellValue, Result};
 
 use chrono::{Datelike, NaiveDate};
 
-use super::helpers::{add_months, coerce_date_value, last_day_of_month};
+use super::helpers::{add_months, coerce_date_value, date_system, last_day_of_month};
 
 pub(crate) async fn eval_days(
     ctx: EvalCtx<'_>,
@@ -17,10 +17,11 @@ pub(crate) async fn eval_days(
         ));
     }
 
+    let system = date_system(ctx);
     let end_val = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     let start_val = evaluate_expression(ctx, current_sheet, &args[1]).await?;
 
-    let end_date = match coerce_date_value(&end_val) {
+    let end_date = match coerce_date_value(system, &end_val) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -28,7 +29,7 @@ pub(crate) async fn eval_days(
             ));
         },
     };
-    let start_date = match coerce_date_value(&start_val) {
+    let start_date = match coerce_date_value(system, &start_val) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -37,8 +38,8 @@ pub(crate) async fn eval_days(
         },
     };
 
-    let diff = (end_date - start_date).num_days();
-    Ok(CellValue::Float(diff as f64))
+    let diff = system.date_to_serial(end_date) - system.date_to_serial(start_date);
+    Ok(CellValue::Float(diff))
 }
 
 pub(crate) async fn eval_days360(
@@ -52,10 +53,11 @@ pub(crate) async fn eval_days360(
         ));
     }
 
+    let system = date_system(ctx);
     let start_val = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     let end_val = evaluate_expression(ctx, current_sheet, &args[1]).await?;
 
-    let start_date = match coerce_date_value(&start_val) {
+    let start_date = match coerce_date_value(system, &start_val) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -63,7 +65,7 @@ pub(crate) async fn eval_days360(
             ));
         },
     };
-    let end_date = match coerce_date_value(&end_val) {
+    let end_date = match coerce_date_value(system, &end_val) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -109,11 +111,12 @@ pub(crate) async fn eval_datedif(
         ));
     }
 
+    let system = date_system(ctx);
     let start_val = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     let end_val = evaluate_expression(ctx, current_sheet, &args[1]).await?;
     let unit_val = evaluate_expression(ctx, current_sheet, &args[2]).await?;
 
-    let mut start_date = match coerce_date_value(&start_val) {
+    let start_date = match coerce_date_value(system, &start_val) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -121,7 +124,7 @@ pub(crate) async fn eval_datedif(
             ));
         },
     };
-    let mut end_date = match coerce_date_value(&end_val) {
+    let end_date = match coerce_date_value(system, &end_val) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -131,7 +134,7 @@ pub(crate) async fn eval_datedif(
     };
 
     if end_date < start_date {
-        std::mem::swap(&mut start_date, &mut end_date);
+        return Ok(CellValue::Error("#NUM!".to_string()));
     }
 
     let unit = match unit_val {
@@ -160,7 +163,7 @@ pub(crate) async fn eval_datedif(
                 ));
             },
         },
-        "D" => (end_date - start_date).num_days() as f64,
+        "D" => system.date_to_serial(end_date) - system.date_to_serial(start_date),
         "MD" => match difference_md(start_date, end_date) {
             Some(v) => v as f64,
             None => {
@@ -355,10 +358,11 @@ pub(crate) async fn eval_yearfrac(
         ));
     }
 
+    let system = date_system(ctx);
     let start_val = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     let end_val = evaluate_expression(ctx, current_sheet, &args[1]).await?;
 
-    let start_date = match coerce_date_value(&start_val) {
+    let start_date = match coerce_date_value(system, &start_val) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -366,7 +370,7 @@ pub(crate) async fn eval_yearfrac(
             ));
         },
     };
-    let end_date = match coerce_date_value(&end_val) {
+    let end_date = match coerce_date_value(system, &end_val) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -406,20 +410,7 @@ pub(crate) async fn eval_yearfrac(
         },
         1 => {
             // Actual/actual
-            let diff_days = (d2 - d1).num_days() as f64;
-            let year1 = d1.year();
-            let year2 = d2.year();
-            let mut days_in_year = 0.0;
-            if year1 == year2 {
-                days_in_year = if is_leap_year(year1) { 366.0 } else { 365.0 };
-            } else {
-                let n_years = (year2 - year1 + 1) as f64;
-                for y in year1..=year2 {
-                    days_in_year += if is_leap_year(y) { 366.0 } else { 365.0 };
-                }
-                days_in_year /= n_years;
-            }
-            diff_days / days_in_year
+            (d2 - d1).num_days() as f64 / actual_year_length(d1, d2)
         },
         2 => {
             // Actual/360
@@ -439,6 +430,35 @@ pub(crate) async fn eval_yearfrac(
     Ok(CellValue::Float(frac))
 }
 
+/// Year length YEARFRAC's actual/actual basis divides by.
+///
+/// Within one calendar year it is that year's length. Spans of at most a
+/// year use 366 when they include a February 29, and longer spans use the
+/// average length of every year they touch.
+fn actual_year_length(start: NaiveDate, end: NaiveDate) -> f64 {
+    let year_length = |year| if is_leap_year(year) { 366.0 } else { 365.0 };
+    let (y1, y2) = (start.year(), end.year());
+    if y1 == y2 {
+        return year_length(y1);
+    }
+
+    let within_a_year = y2 == y1 + 1 && (start.month(), start.day()) >= (end.month(), end.day());
+    if within_a_year {
+        let includes_leap_day = |year| {
+            NaiveDate::from_ymd_opt(year, 2, 29)
+                .is_some_and(|leap_day| start <= leap_day && leap_day <= end)
+        };
+        return if includes_leap_day(y1) || includes_leap_day(y2) {
+            366.0
+        } else {
+            365.0
+        };
+    }
+
+    let total: f64 = (y1..=y2).map(year_length).sum();
+    total / (y2 - y1 + 1) as f64
+}
+
 fn is_leap_year(year: i32) -> bool {
     year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
 }
@@ -1,8 +1,8 @@
 use crate::sheet::eval::engine::{EvalCtx, evaluate_expression, flatten_range_expr, to_number};
 use crate::sheet::eval::parser::Expr;
-use crate::sheet::{CellValue, Result};
+use crate::sheet::{CellValue, DateSystem, Result};
 
-use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Weekday};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
 
 pub(crate) const SECONDS_PER_DAY: f64 = 86_400.0;
 
@@ -15,94 +15,98 @@ pub(super) async fn number_arg(
     Ok(to_number(&v))
 }
 
-pub(super) fn date_to_excel_serial_1900(date: NaiveDate) -> f64 {
-    let base = NaiveDate::from_ymd_opt(1899, 12, 30).expect("Invalid Excel 1900 base date");
-    let days = (date - base).num_days();
-    days as f64
+/// Date system of the workbook being evaluated.
+pub(super) fn date_system(ctx: EvalCtx<'_>) -> DateSystem {
+    DateSystem::from_1904_flag(ctx.is_1904_date_system())
 }
 
-pub(super) fn datetime_to_excel_serial_1900(dt: NaiveDateTime) -> f64 {
-    let date_serial = date_to_excel_serial_1900(dt.date());
-    let seconds = dt.time().num_seconds_from_midnight() as f64;
-    date_serial + seconds / SECONDS_PER_DAY
-}
-
-pub(super) fn serial_to_excel_date_1900(serial: f64) -> Option<NaiveDate> {
-    let base = NaiveDate::from_ymd_opt(1899, 12, 30)?;
-    let days = serial.floor() as i64;
-    base.checked_add_signed(Duration::days(days))
-}
-
-pub(super) fn make_date_serial_1900(year: f64, month: f64, day: f64) -> Option<f64> {
-    let y = year.trunc() as i32;
-    let m = month.trunc() as i32;
-    let d = day.trunc() as i64;
-
-    // Excel-style month normalization: months can be outside 1..=12.
-    let mut ym = (y, m - 1); // month 0-based
-    ym.0 += ym.1.div_euclid(12);
-    ym.1 = ym.1.rem_euclid(12);
-    let norm_year = ym.0;
-    let norm_month = (ym.1 + 1) as u32;
-
-    let first_of_month = NaiveDate::from_ymd_opt(norm_year, norm_month, 1)?;
-    let target = first_of_month.checked_add_signed(Duration::days(d - 1))?;
-    Some(date_to_excel_serial_1900(target))
+/// Serial for `DATE(year, month, day)`.
+///
+/// Years 0 through 1899 are offset from 1900, as in Excel. Months and days
+/// outside their usual range carry over into neighbouring months and years.
+pub(super) fn make_date_serial(system: DateSystem, year: f64, month: f64, day: f64) -> Option<f64> {
+    let mut year = year.trunc();
+    if !(0.0..10_000.0).contains(&year) || !month.is_finite() || !day.is_finite() {
+        return None;
+    }
+    if year < 1900.0 {
+        year += 1900.0;
+    }
+    system.ymd_to_serial(year as i32, month.trunc() as i64, day.trunc() as i64)
 }
 
 pub(super) fn parse_date_string(s: &str) -> Option<NaiveDate> {
-    if let Ok(d) = NaiveDate::parse_from_str(s, "%Y-%m-%d") {
-        return Some(d);
-    }
-    if let Ok(d) = NaiveDate::parse_from_str(s, "%m/%d/%Y") {
-        return Some(d);
-    }
-    None
+    const FORMATS: &[&str] = &[
+        "%Y-%m-%d",
+        "%Y/%m/%d",
+        "%m/%d/%Y",
+        "%d-%b-%Y",
+        "%d %b %Y",
+        "%d-%B-%Y",
+        "%d %B %Y",
+        "%b %d, %Y",
+        "%B %d, %Y",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(s, fmt).ok())
 }
 
 pub(super) fn parse_time_string(s: &str) -> Option<NaiveTime> {
-    if let Ok(t) = NaiveTime::parse_from_str(s, "%H:%M:%S") {
-        return Some(t);
-    }
-    if let Ok(t) = NaiveTime::parse_from_str(s, "%H:%M") {
-        return Some(t);
-    }
-    None
+    const FORMATS: &[&str] = &[
+        "%H:%M:%S%.f",
+        "%H:%M",
+        "%I:%M:%S%.f %p",
+        "%I:%M %p",
+        "%I %p",
+    ];
+    FORMATS
+        .iter()
+        .find_map(|fmt| NaiveTime::parse_from_str(s, fmt).ok())
 }
 
+/// Parse a date followed by a time, separated by whitespace or an ISO `T`.
 pub(super) fn parse_datetime_string(s: &str) -> Option<NaiveDateTime> {
-    const FORMATS: &[&str] = &[
-        "%Y-%m-%d %H:%M:%S",
-        "%Y-%m-%d %H:%M",
-        "%m/%d/%Y %H:%M:%S",
-        "%m/%d/%Y %H:%M",
-    ];
-    for fmt in FORMATS {
-        if let Ok(dt) = NaiveDateTime::parse_from_str(s, fmt) {
-            return Some(dt);
-        }
+    if let Ok(dt) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some(dt);
     }
-    None
+    // Dates such as "January 15, 2024" contain spaces themselves, so try
+    // every split point rather than only the first
+    s.char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .find_map(|(idx, _)| {
+            let date = parse_date_string(s[..idx].trim_end())?;
+            let time = parse_time_string(s[idx..].trim_start())?;
+            Some(date.and_time(time))
+        })
 }
 
-pub(super) fn coerce_date_value(value: &CellValue) -> Option<NaiveDate> {
-    if let Some(n) = to_number(value)
-        && let Some(date) = serial_to_excel_date_1900(n)
-    {
-        return Some(date);
+/// Whole-day serial of a date argument, from either a number or date text.
+///
+/// Numbers are truncated to their day; negative serials and serials past
+/// 9999-12-31 are rejected.
+pub(super) fn coerce_date_serial(system: DateSystem, value: &CellValue) -> Option<f64> {
+    if let Some(n) = to_number(value) {
+        return system.serial_to_ymd(n).map(|_| n.floor());
     }
     if let CellValue::String(s) = value {
         let trimmed = s.trim();
-        if let Some(dt) = parse_datetime_string(trimmed) {
-            return Some(dt.date());
-        }
-        if let Some(d) = parse_date_string(trimmed) {
-            return Some(d);
-        }
+        let date = parse_datetime_string(trimmed)
+            .map(|dt| dt.date())
+            .or_else(|| parse_date_string(trimmed))?;
+        return Some(system.date_to_serial(date));
     }
     None
 }
 
+/// Calendar date of a date argument.
+///
+/// Serial 60 of the 1900 date system, the fictitious 1900-02-29, has no
+/// calendar date and gives `None`.
+pub(super) fn coerce_date_value(system: DateSystem, value: &CellValue) -> Option<NaiveDate> {
+    system.serial_to_date(coerce_date_serial(system, value)?)
+}
+
 pub(super) fn coerce_time_fraction(value: &CellValue) -> Option<f64> {
     if let Some(n) = to_number(value) {
         let frac = n.fract();
@@ -110,39 +114,66 @@ pub(super) fn coerce_time_fraction(value: &CellValue) -> Option<f64> {
     }
     if let CellValue::String(s) = value {
         let trimmed = s.trim();
-        if let Some(dt) = parse_datetime_string(trimmed) {
-            return Some(dt.time().num_seconds_from_midnight() as f64 / SECONDS_PER_DAY);
-        }
-        if let Some(t) = parse_time_string(trimmed) {
-            return Some(t.num_seconds_from_midnight() as f64 / SECONDS_PER_DAY);
-        }
+        let time = parse_datetime_string(trimmed)
+            .map(|dt| dt.time())
+            .or_else(|| parse_time_string(trimmed))?;
+        return Some(time_fraction(time));
     }
     None
 }
 
-pub(super) fn weekday_number(weekday: Weekday, return_type: i32) -> Option<i64> {
-    let num = match return_type {
-        1 => weekday.num_days_from_sunday() as i64 + 1,
-        2 => weekday.num_days_from_monday() as i64 + 1,
-        3 => weekday.num_days_from_monday() as i64,
-        _ => return None,
-    };
-    Some(num)
+/// Fraction of a day elapsed at a time of day.
+pub(super) fn time_fraction(time: NaiveTime) -> f64 {
+    let millis = (time - NaiveTime::MIN).num_milliseconds() as f64;
+    millis / (SECONDS_PER_DAY * 1000.0)
+}
+
+/// Hour, minute and second of a time fraction, rounded to the nearest second.
+pub(super) fn time_parts(frac: f64) -> (i64, i64, i64) {
+    let seconds = ((frac * SECONDS_PER_DAY).round() as i64).rem_euclid(SECONDS_PER_DAY as i64);
+    (seconds / 3600, seconds / 60 % 60, seconds % 60)
 }
 
-pub(super) fn weeknum_value(date: NaiveDate, return_type: i32) -> Option<i64> {
-    let start_weekday = match return_type {
-        1 => Weekday::Sun,
-        2 => Weekday::Mon,
+/// First day of the week for a `WEEKDAY` or `WEEKNUM` return type.
+///
+/// Types 1 and 2 start on Sunday and Monday; 11 through 17 start on Monday
+/// through Sunday.
+pub(super) fn week_start(return_type: i32) -> Option<Weekday> {
+    Some(match return_type {
+        1 | 17 => Weekday::Sun,
+        2 | 11 => Weekday::Mon,
+        12 => Weekday::Tue,
+        13 => Weekday::Wed,
+        14 => Weekday::Thu,
+        15 => Weekday::Fri,
+        16 => Weekday::Sat,
         _ => return None,
-    };
-    let first_day = NaiveDate::from_ymd_opt(date.year(), 1, 1)?;
-    let mut week_start = first_day;
-    while week_start.weekday() != start_weekday {
-        week_start = week_start.checked_sub_signed(Duration::days(1))?;
+    })
+}
+
+pub(super) fn weekday_number(weekday: Weekday, return_type: i32) -> Option<i64> {
+    if return_type == 3 {
+        return Some(weekday.num_days_from_monday() as i64);
     }
-    let days = (date - week_start).num_days();
-    Some(days / 7 + 1)
+    let start = week_start(return_type)?;
+    Some(days_since(start, weekday) + 1)
+}
+
+/// Week of the year containing `serial`, with weeks starting on
+/// `start_weekday` and week 1 containing January 1.
+pub(super) fn weeknum_value(
+    system: DateSystem,
+    serial: f64,
+    start_weekday: Weekday,
+) -> Option<i64> {
+    let (year, _, _) = system.serial_to_ymd(serial)?;
+    let jan1 = system.ymd_to_serial(year, 1, 1)?;
+    let offset = days_since(start_weekday, system.weekday(jan1)?);
+    Some(((serial - jan1) as i64 + offset) / 7 + 1)
+}
+
+fn days_since(start: Weekday, weekday: Weekday) -> i64 {
+    (weekday.num_days_from_monday() as i64 - start.num_days_from_monday() as i64).rem_euclid(7)
 }
 
 pub(super) fn last_day_of_month(date: NaiveDate) -> Option<NaiveDate> {
@@ -179,11 +210,12 @@ pub(super) async fn collect_holiday_dates(
     current_sheet: &str,
     expr: &Expr,
 ) -> Result<Vec<NaiveDate>> {
+    let system = date_system(ctx);
     let range = flatten_range_expr(ctx, current_sheet, expr).await?;
     let mut out = Vec::new();
     for v in &range.values {
         if let Some(n) = to_number(v)
-            && let Some(d) = serial_to_excel_date_1900(n)
+            && let Some(d) = system.serial_to_date(n)
         {
             out.push(d);
         }
@@ -215,6 +247,31 @@ impl Default for WeekendConfig {
 }
 
 impl WeekendConfig {
+    /// Weekend argument of `WORKDAY.INTL` and `NETWORKDAYS.INTL`.
+    ///
+    /// Unknown weekend codes give `#NUM!`. Patterns must be seven `0`/`1`
+    /// characters, Monday first, with at least one working day; anything
+    /// else gives `#VALUE!`.
+    pub(super) fn from_value(value: CellValue) -> std::result::Result<Self, CellValue> {
+        match value {
+            CellValue::Empty => Ok(WeekendConfig::default()),
+            CellValue::String(s) => {
+                let valid =
+                    s.len() == 7 && s.bytes().all(|b| b == b'0' || b == b'1') && s.contains('0');
+                if valid {
+                    Ok(WeekendConfig::Pattern(s))
+                } else {
+                    Err(CellValue::Error("#VALUE!".to_string()))
+                }
+            },
+            other => match to_number(&other).map(|n| n.trunc() as i32) {
+                Some(code @ (1..=7 | 11..=17)) => Ok(WeekendConfig::Code(code)),
+                Some(_) => Err(CellValue::Error("#NUM!".to_string())),
+                None => Err(CellValue::Error("#VALUE!".to_string())),
+            },
+        }
+    }
+
     pub(super) fn is_weekend(&self, weekday: Weekday) -> bool {
         match self {
             WeekendConfig::Code(c) => match c {
@@ -2,9 +2,7 @@ use crate::sheet::eval::engine::EvalCtx;
 use crate::sheet::eval::parser::Expr;
 use crate::sheet::{CellValue, Result};
 
-use super::helpers::{
-    add_months, date_to_excel_serial_1900, last_day_of_month, number_arg, serial_to_excel_date_1900,
-};
+use super::helpers::{add_months, date_system, number_arg};
 
 pub(crate) async fn eval_edate(
     ctx: EvalCtx<'_>,
@@ -17,6 +15,7 @@ pub(crate) async fn eval_edate(
         ));
     }
 
+    let system = date_system(ctx);
     let start = match number_arg(ctx, current_sheet, &args[0]).await? {
         Some(n) => n,
         None => {
@@ -32,7 +31,7 @@ pub(crate) async fn eval_edate(
         },
     };
 
-    let date = match serial_to_excel_date_1900(start) {
+    let date = match system.serial_to_date(start) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -48,7 +47,7 @@ pub(crate) async fn eval_edate(
         },
     };
 
-    let serial = date_to_excel_serial_1900(shifted);
+    let serial = system.date_to_serial(shifted);
     Ok(CellValue::DateTime(serial))
 }
 
@@ -63,6 +62,7 @@ pub(crate) async fn eval_eomonth(
         ));
     }
 
+    let system = date_system(ctx);
     let start = match number_arg(ctx, current_sheet, &args[0]).await? {
         Some(n) => n,
         None => {
@@ -80,8 +80,8 @@ pub(crate) async fn eval_eomonth(
         },
     };
 
-    let date = match serial_to_excel_date_1900(start) {
-        Some(d) => d,
+    let (year, month, _) = match system.serial_to_ymd(start) {
+        Some(ymd) => ymd,
         None => {
             return Ok(CellValue::Error(
                 "EOMONTH start_date is not a valid date".to_string(),
@@ -89,17 +89,11 @@ pub(crate) async fn eval_eomonth(
         },
     };
 
-    let shifted = match add_months(date, months.trunc() as i32) {
-        Some(d) => d,
-        None => {
-            return Ok(CellValue::Error(
-                "EOMONTH result is out of range".to_string(),
-            ));
-        },
-    };
-
-    let last_day = match last_day_of_month(shifted) {
-        Some(d) => d,
+    // Day 0 of the month after the target month is the target's last day.
+    // In the 1900 date system this makes February 1900 end on serial 60.
+    let months = i64::from(month) + months.trunc() as i64 + 1;
+    let serial = match system.ymd_to_serial(year, months, 0) {
+        Some(s) => s,
         None => {
             return Ok(CellValue::Error(
                 "EOMONTH result is out of range".to_string(),
@@ -107,7 +101,6 @@ pub(crate) async fn eval_eomonth(
         },
     };
 
-    let serial = date_to_excel_serial_1900(last_day);
     Ok(CellValue::DateTime(serial))
 }
 
@@ -4,7 +4,9 @@ use crate::sheet::{CellValue, Result};
 
 use chrono::Datelike;
 
-use super::helpers::{coerce_date_value, weekday_number, weeknum_value};
+use super::helpers::{
+    coerce_date_serial, coerce_date_value, date_system, week_start, weekday_number, weeknum_value,
+};
 
 pub(crate) async fn eval_weekday(
     ctx: EvalCtx<'_>,
@@ -16,8 +18,9 @@ pub(crate) async fn eval_weekday(
             "WEEKDAY expects 1 or 2 arguments (serial_number, [return_type])".to_string(),
         ));
     }
+    let system = date_system(ctx);
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
-    let date = match coerce_date_value(&value) {
+    let weekday = match coerce_date_serial(system, &value).and_then(|s| system.weekday(s)) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -28,17 +31,17 @@ pub(crate) async fn eval_weekday(
     let return_type = if args.len() == 2 {
         let rt_val = evaluate_expression(ctx, current_sheet, &args[1]).await?;
         match to_number(&rt_val) {
-            Some(n) if matches!(n as i32, 1..=3) => n as i32,
+            Some(n) if matches!(n as i32, 1..=3 | 11..=17) => n as i32,
             _ => {
                 return Ok(CellValue::Error(
-                    "WEEKDAY return_type must be 1, 2, or 3".to_string(),
+                    "WEEKDAY return_type must be 1, 2, or 3, or 11 to 17".to_string(),
                 ));
             },
         }
     } else {
         1
     };
-    let number = match weekday_number(date.weekday(), return_type) {
+    let number = match weekday_number(weekday, return_type) {
         Some(n) => n,
         None => {
             return Ok(CellValue::Error(
@@ -59,9 +62,10 @@ pub(crate) async fn eval_weeknum(
             "WEEKNUM expects 1 or 2 arguments (serial_number, [return_type])".to_string(),
         ));
     }
+    let system = date_system(ctx);
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
-    let date = match coerce_date_value(&value) {
-        Some(d) => d,
+    let serial = match coerce_date_serial(system, &value) {
+        Some(s) => s,
         None => {
             return Ok(CellValue::Error(
                 "WEEKNUM expects a valid date serial or text".to_string(),
@@ -71,20 +75,27 @@ pub(crate) async fn eval_weeknum(
     let return_type = if args.len() == 2 {
         let rt_val = evaluate_expression(ctx, current_sheet, &args[1]).await?;
         match to_number(&rt_val) {
-            Some(n) if matches!(n as i32, 1 | 2) => n as i32,
+            Some(n) if matches!(n as i32, 1 | 2 | 11..=17 | 21) => n as i32,
             _ => {
                 return Ok(CellValue::Error(
-                    "WEEKNUM return_type must be 1 or 2".to_string(),
+                    "WEEKNUM return_type must be 1 or 2, 11 to 17, or 21".to_string(),
                 ));
             },
         }
     } else {
         1
     };
-    match weeknum_value(date, return_type) {
+    // Return type 21 numbers weeks the ISO 8601 way, like ISOWEEKNUM
+    let week = match week_start(return_type) {
+        Some(start) => weeknum_value(system, serial, start),
+        None => system
+            .serial_to_date(serial)
+            .map(|d| d.iso_week().week() as i64),
+    };
+    match week {
         Some(week) => Ok(CellValue::Int(week)),
         None => Ok(CellValue::Error(
-            "WEEKNUM return_type not supported".to_string(),
+            "WEEKNUM expects a valid date serial or text".to_string(),
         )),
     }
 }
@@ -100,7 +111,7 @@ pub(crate) async fn eval_isoweeknum(
         ));
     }
     let value = evaluate_expression(ctx, current_sheet, &args[0]).await?;
-    let date = match coerce_date_value(&value) {
+    let date = match coerce_date_value(date_system(ctx), &value) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -1,10 +1,9 @@
-use crate::sheet::eval::engine::{EvalCtx, evaluate_expression, to_number};
+use crate::sheet::eval::engine::{EvalCtx, evaluate_expression};
 use crate::sheet::eval::parser::Expr;
 use crate::sheet::{CellValue, Result};
 
 use super::helpers::{
-    WeekendConfig, collect_holiday_dates, date_to_excel_serial_1900, networkdays_core, number_arg,
-    serial_to_excel_date_1900, workday_core,
+    WeekendConfig, collect_holiday_dates, date_system, networkdays_core, number_arg, workday_core,
 };
 
 pub(crate) async fn eval_workday(
@@ -18,6 +17,7 @@ pub(crate) async fn eval_workday(
         ));
     }
 
+    let system = date_system(ctx);
     let start = match number_arg(ctx, current_sheet, &args[0]).await? {
         Some(n) => n,
         None => {
@@ -33,7 +33,7 @@ pub(crate) async fn eval_workday(
         },
     };
 
-    let start_date = match serial_to_excel_date_1900(start) {
+    let start_date = match system.serial_to_date(start) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -58,7 +58,7 @@ pub(crate) async fn eval_workday(
         },
     };
 
-    let serial = date_to_excel_serial_1900(result);
+    let serial = system.date_to_serial(result);
     Ok(CellValue::DateTime(serial))
 }
 
@@ -74,6 +74,7 @@ pub(crate) async fn eval_workday_intl(
         ));
     }
 
+    let system = date_system(ctx);
     let start = match number_arg(ctx, current_sheet, &args[0]).await? {
         Some(n) => n,
         None => {
@@ -93,18 +94,15 @@ pub(crate) async fn eval_workday_intl(
 
     let weekend = if args.len() >= 3 {
         let weekend_val = evaluate_expression(ctx, current_sheet, &args[2]).await?;
-        match weekend_val {
-            CellValue::String(s) => WeekendConfig::Pattern(s),
-            _ => match to_number(&weekend_val) {
-                Some(n) => WeekendConfig::Code(n.trunc() as i32),
-                None => return Ok(CellValue::Error("#VALUE!".to_string())),
-            },
+        match WeekendConfig::from_value(weekend_val) {
+            Ok(weekend) => weekend,
+            Err(err) => return Ok(err),
         }
     } else {
         WeekendConfig::default()
     };
 
-    let start_date = match serial_to_excel_date_1900(start) {
+    let start_date = match system.serial_to_date(start) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -128,7 +126,7 @@ pub(crate) async fn eval_workday_intl(
         },
     };
 
-    let serial = date_to_excel_serial_1900(result);
+    let serial = system.date_to_serial(result);
     Ok(CellValue::DateTime(serial))
 }
 
@@ -143,6 +141,7 @@ pub(crate) async fn eval_networkdays(
         ));
     }
 
+    let system = date_system(ctx);
     let start = match number_arg(ctx, current_sheet, &args[0]).await? {
         Some(n) => n,
         None => {
@@ -160,7 +159,7 @@ pub(crate) async fn eval_networkdays(
         },
     };
 
-    let start_date = match serial_to_excel_date_1900(start) {
+    let start_date = match system.serial_to_date(start) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -168,7 +167,7 @@ pub(crate) async fn eval_networkdays(
             ));
         },
     };
-    let end_date = match serial_to_excel_date_1900(end) {
+    let end_date = match system.serial_to_date(end) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -200,6 +199,7 @@ pub(crate) async fn eval_networkdays_intl(
         ));
     }
 
+    let system = date_system(ctx);
     let start = match number_arg(ctx, current_sheet, &args[0]).await? {
         Some(n) => n,
         None => {
@@ -219,18 +219,15 @@ pub(crate) async fn eval_networkdays_intl(
 
     let weekend = if args.len() >= 3 {
         let weekend_val = evaluate_expression(ctx, current_sheet, &args[2]).await?;
-        match weekend_val {
-            CellValue::String(s) => WeekendConfig::Pattern(s),
-            _ => match to_number(&weekend_val) {
-                Some(n) => WeekendConfig::Code(n.trunc() as i32),
-                None => return Ok(CellValue::Error("#VALUE!".to_string())),
-            },
+        match WeekendConfig::from_value(weekend_val) {
+            Ok(weekend) => weekend,
+            Err(err) => return Ok(err),
         }
     } else {
         WeekendConfig::default()
     };
 
-    let start_date = match serial_to_excel_date_1900(start) {
+    let start_date = match system.serial_to_date(start) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -238,7 +235,7 @@ pub(crate) async fn eval_networkdays_intl(
             ));
         },
     };
-    let end_date = match serial_to_excel_date_1900(end) {
+    let end_date = match system.serial_to_date(end) {
         Some(d) => d,
         None => {
             return Ok(CellValue::Error(
@@ -242,6 +242,7 @@ register_functions!(
     wrap_textbefore => super::text::eval_textbefore => ["TEXTBEFORE"],
     wrap_textafter => super::text::eval_textafter => ["TEXTAFTER"],
     wrap_textsplit => super::text::eval_textsplit => ["TEXTSPLIT"],
+    wrap_regex => super::text::eval_regex => ["REGEX"],
     wrap_unichar => super::text::eval_unichar => ["UNICHAR"],
     wrap_unicode => super::text::eval_unicode => ["UNICODE"],
     wrap_arabic => super::text::eval_arabic => ["ARABIC"],
@@ -0,0 +1,190 @@
+#![cfg(all(test, feature = "eval_engine"))]
+
+//! Date and time functions checked against results produced by Excel.
+
+use crate::sheet::CellValue;
+use crate::sheet::eval::engine::evaluate_expression;
+use crate::sheet::eval::engine::test_helpers::TestEngine;
+use crate::sheet::eval::parser::parse_expression;
+
+const TOL: f64 = 1e-8;
+
+async fn eval(engine: &TestEngine, formula: &str) -> CellValue {
+    let expr =
+        parse_expression("Sheet1", formula).unwrap_or_else(|| panic!("failed to parse {formula}"));
+    evaluate_expression(engine.ctx(), "Sheet1", &expr)
+        .await
+        .unwrap_or_else(|err| panic!("failed to evaluate {formula}: {err}"))
+}
+
+async fn assert_table(engine: &TestEngine, table: &[(&str, f64)]) {
+    for &(formula, expected) in table {
+        let actual = match eval(engine, formula).await {
+            CellValue::Int(n) => n as f64,
+            CellValue::Float(n) | CellValue::DateTime(n) => n,
+            other => panic!("{formula}: expected {expected}, got {other:?}"),
+        };
+        assert!(
+            (actual - expected).abs() < TOL,
+            "{formula}: expected {expected}, got {actual}"
+        );
+    }
+}
+
+async fn assert_error(engine: &TestEngine, formula: &str, code: &str) {
+    match eval(engine, formula).await {
+        CellValue::Error(e) => assert!(e.contains(code), "{formula}: got error {e}"),
+        other => panic!("{formula}: expected {code}, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn date_functions_match_excel_1900() {
+    let engine = TestEngine::new();
+    engine.set_cell("Sheet1", 1, 2, CellValue::DateTime(39777.0)); // 2008-11-26
+    engine.set_cell("Sheet1", 2, 2, CellValue::DateTime(39786.0)); // 2008-12-04
+    engine.set_cell("Sheet1", 3, 2, CellValue::DateTime(39834.0)); // 2009-01-21
+    engine.set_cell("Sheet1", 1, 3, CellValue::DateTime(41235.0)); // 2012-11-22
+
+    assert_table(
+        &engine,
+        &[
+            // The fictitious 1900-02-29 sits between serials 59 and 61
+            ("DATE(1900,1,1)", 1.0),
+            ("DATE(1900,2,28)", 59.0),
+            ("DATE(1900,2,29)", 60.0),
+            ("DATE(1900,3,1)", 61.0),
+            ("DATE(2024,1,15)", 45306.0),
+            ("DATE(108,1,2)", 39449.0),
+            ("DATE(2008,14,2)", 39846.0),
+            ("DATE(2008,1,35)", 39482.0),
+            ("YEAR(60)", 1900.0),
+            ("MONTH(60)", 2.0),
+            ("DAY(60)", 29.0),
+            ("DAY(0)", 0.0),
+            ("MONTH(0)", 1.0),
+            ("YEAR(45306)", 2024.0),
+            ("DAY(\"15-Apr-2011\")", 15.0),
+            ("DATEVALUE(\"2008-01-01\")", 39448.0),
+            ("DATEVALUE(\"1/30/2008\")", 39477.0),
+            ("DATEVALUE(\"22-Aug-2008\")", 39682.0),
+            ("DATEVALUE(\"August 22, 2008\")", 39682.0),
+            ("DATEVALUE(\"2008/02/23\")", 39501.0),
+            ("DATEVALUE(\"2008-01-01 10:30\")", 39448.0),
+            ("TIMEVALUE(\"2:24 AM\")", 0.1),
+            ("TIMEVALUE(\"22-Aug-2008 6:35 AM\")", 0.274305555555556),
+            ("TIMEVALUE(\"18:00:00\")", 0.75),
+            ("HOUR(0.75)", 18.0),
+            ("HOUR(\"3:30:30 PM\")", 15.0),
+            ("MINUTE(\"12:45:00 PM\")", 45.0),
+            ("SECOND(\"4:48:18 PM\")", 18.0),
+            ("WEEKDAY(1)", 1.0),
+            ("WEEKDAY(0)", 7.0),
+            ("WEEKDAY(61)", 5.0),
+            ("WEEKDAY(DATE(2008,2,14))", 5.0),
+            ("WEEKDAY(DATE(2008,2,14),2)", 4.0),
+            ("WEEKDAY(DATE(2008,2,14),3)", 3.0),
+            ("WEEKDAY(DATE(2008,2,14),16)", 6.0),
+            ("WEEKNUM(DATE(2012,3,9))", 10.0),
+            ("WEEKNUM(DATE(2012,3,9),2)", 11.0),
+            ("WEEKNUM(DATE(2012,3,9),21)", 10.0),
+            ("WEEKNUM(DATE(2012,1,1),2)", 1.0),
+            ("WEEKNUM(DATE(2012,1,2),2)", 2.0),
+            ("ISOWEEKNUM(DATE(2012,3,9))", 10.0),
+            ("EDATE(DATE(2011,1,15),1)", 40589.0),
+            ("EDATE(DATE(2011,1,15),-1)", 40527.0),
+            ("EDATE(DATE(2011,1,31),1)", 40602.0),
+            ("EOMONTH(DATE(2011,1,1),1)", 40602.0),
+            ("EOMONTH(DATE(2011,1,1),-3)", 40482.0),
+            ("EOMONTH(DATE(1900,1,15),1)", 60.0),
+            ("DAYS(DATE(2021,3,15),DATE(2021,2,1))", 42.0),
+            ("DAYS(DATE(1900,3,1),DATE(1900,2,28))", 2.0),
+            ("DAYS360(DATE(2011,1,30),DATE(2011,12,31))", 330.0),
+            ("NETWORKDAYS(DATE(2012,10,1),DATE(2013,3,1))", 110.0),
+            ("NETWORKDAYS(DATE(2012,10,1),DATE(2013,3,1),C1)", 109.0),
+            ("NETWORKDAYS.INTL(DATE(2006,1,1),DATE(2006,1,31))", 22.0),
+            ("NETWORKDAYS.INTL(DATE(2006,2,28),DATE(2006,1,31))", -21.0),
+            ("NETWORKDAYS.INTL(DATE(2006,2,1),DATE(2006,2,28),7)", 20.0),
+            (
+                "NETWORKDAYS.INTL(DATE(2006,2,1),DATE(2006,2,28),\"0010001\")",
+                20.0,
+            ),
+            ("WORKDAY(DATE(2008,10,1),151)", 39933.0),
+            ("WORKDAY(DATE(2008,10,1),151,B1:B3)", 39938.0),
+            ("WORKDAY.INTL(DATE(2012,1,1),90,11)", 41013.0),
+            ("WORKDAY.INTL(DATE(2012,1,1),30,17)", 40944.0),
+            ("DATEDIF(DATE(2001,1,1),DATE(2003,1,1),\"Y\")", 2.0),
+            ("DATEDIF(DATE(2001,6,1),DATE(2002,8,15),\"D\")", 440.0),
+            ("DATEDIF(DATE(2001,6,1),DATE(2002,8,15),\"YD\")", 75.0),
+            ("DATEDIF(DATE(2001,6,1),DATE(2002,8,15),\"M\")", 14.0),
+            ("DATEDIF(DATE(2001,6,1),DATE(2002,8,15),\"MD\")", 14.0),
+            (
+                "YEARFRAC(DATE(2012,1,1),DATE(2012,7,30))",
+                0.580555555555556,
+            ),
+            (
+                "YEARFRAC(DATE(2012,1,1),DATE(2012,7,30),1)",
+                0.576502732240437,
+            ),
+            (
+                "YEARFRAC(DATE(2012,1,1),DATE(2012,7,30),3)",
+                0.578082191780822,
+            ),
+            (
+                "YEARFRAC(DATE(2011,7,1),DATE(2012,3,15),1)",
+                0.704918032786885,
+            ),
+            (
+                "YEARFRAC(DATE(2010,7,1),DATE(2012,3,15),1)",
+                1.70529197080292,
+            ),
+        ],
+    )
+    .await;
+
+    assert_error(&engine, "DATE(-1,1,1)", "out of range").await;
+    assert_error(&engine, "DATE(10000,1,1)", "out of range").await;
+    assert_error(
+        &engine,
+        "DATEDIF(DATE(2002,1,1),DATE(2001,1,1),\"D\")",
+        "#NUM!",
+    )
+    .await;
+    assert_error(&engine, "WORKDAY.INTL(DATE(2012,1,1),30,0)", "#NUM!").await;
+    assert_error(
+        &engine,
+        "WORKDAY.INTL(DATE(2012,1,1),30,\"1111111\")",
+        "#VALUE!",
+    )
+    .await;
+    assert_error(&engine, "NETWORKDAYS.INTL(1,30,\"00011\")", "#VALUE!").await;
+}
+
+#[tokio::test]
+async fn date_functions_follow_1904_date_system() {
+    let engine = TestEngine::new();
+    engine.set_1904_date_system(true);
+
+    assert_table(
+        &engine,
+        &[
+            ("DATE(2024,1,15)", 43844.0),
+            ("DATE(1904,1,1)", 0.0),
+            ("DATE(1904,3,1)", 60.0),
+            ("YEAR(0)", 1904.0),
+            ("MONTH(0)", 1.0),
+            ("DAY(0)", 1.0),
+            ("DAY(59)", 29.0),
+            ("WEEKDAY(0)", 6.0),
+            ("DATEVALUE(\"2008-01-01\")", 37986.0),
+            ("EOMONTH(DATE(2011,1,1),1)", 39140.0),
+            ("EDATE(DATE(2011,1,15),1)", 39127.0),
+            ("WEEKNUM(DATE(2012,3,9))", 10.0),
+            ("WORKDAY(DATE(2008,10,1),151)", 38471.0),
+            ("NETWORKDAYS(DATE(2012,10,1),DATE(2013,3,1))", 110.0),
+        ],
+    )
+    .await;
+
+    assert_error(&engine, "DATE(1903,12,31)", "out of range").await;
+}
@@ -1,3 +1,5 @@
 mod aggregate_logical;
+mod date_time;
 mod financial;
 mod lookup_text;
+mod text;
@@ -0,0 +1,175 @@
+#![cfg(all(test, feature = "eval_engine"))]
+
+//! Text functions checked against results produced by Excel, including
+//! lengths and positions in UTF-16 code units for emoji and CJK text.
+
+use crate::sheet::CellValue;
+use crate::sheet::eval::engine::evaluate_expression;
+use crate::sheet::eval::engine::test_helpers::TestEngine;
+use crate::sheet::eval::parser::parse_expression;
+
+async fn eval(engine: &TestEngine, formula: &str) -> CellValue {
+    let expr =
+        parse_expression("Sheet1", formula).unwrap_or_else(|| panic!("failed to parse {formula}"));
+    evaluate_expression(engine.ctx(), "Sheet1", &expr)
+        .await
+        .unwrap_or_else(|err| panic!("failed to evaluate {formula}: {err}"))
+}
+
+async fn assert_table(engine: &TestEngine, table: &[(&str, CellValue)]) {
+    for (formula, expected) in table {
+        let actual = eval(engine, formula).await;
+        assert_eq!(&actual, expected, "{formula}");
+    }
+}
+
+fn s(text: &str) -> CellValue {
+    CellValue::String(text.to_string())
+}
+
+fn n(value: i64) -> CellValue {
+    CellValue::Int(value)
+}
+
+#[tokio::test]
+async fn text_functions_match_excel() {
+    let engine = TestEngine::new();
+    engine.set_cell("Sheet1", 1, 1, s("Alpha"));
+    engine.set_cell("Sheet1", 2, 1, s(""));
+    engine.set_cell("Sheet1", 3, 1, s("Gamma"));
+
+    assert_table(
+        &engine,
+        &[
+            ("LEFT(\"Sale Price\",4)", s("Sale")),
+            ("LEFT(\"Sweden\")", s("S")),
+            ("LEFT(\"Sale Price\",4.9)", s("Sale")),
+            ("RIGHT(\"Sale Price\",5.5)", s("Price")),
+            ("MID(\"Fluid Flow\",7,20)", s("Flow")),
+            ("MID(\"Fluid Flow\",1.9,5.2)", s("Fluid")),
+            ("MID(\"Fluid Flow\",20,5)", s("")),
+            ("LEN(\"Phoenix, AZ\")", n(11)),
+            ("LEN(\"\")", n(0)),
+            ("FIND(\"M\",\"Miriam McGovern\")", n(1)),
+            ("FIND(\"m\",\"Miriam McGovern\")", n(6)),
+            ("FIND(\"M\",\"Miriam McGovern\",3)", n(8)),
+            ("FIND(\"\",\"abc\",2)", n(2)),
+            ("SEARCH(\"e\",\"Statements\",6)", n(7)),
+            ("SEARCH(\"margin\",\"Profit Margin\")", n(8)),
+            ("SEARCH(\"p?o\",\"Profit Margin\")", n(1)),
+            ("SEARCH(\"m*n\",\"Profit Margin\")", n(8)),
+            ("SEARCH(\"~*\",\"2*3\")", n(2)),
+            (
+                "SUBSTITUTE(\"Quarter 1, 2008\",\"1\",\"2\",1)",
+                s("Quarter 2, 2008"),
+            ),
+            (
+                "SUBSTITUTE(\"Quarter 1, 2011\",\"1\",\"2\",3)",
+                s("Quarter 1, 2012"),
+            ),
+            (
+                "SUBSTITUTE(\"Sales Data\",\"Sales\",\"Cost\")",
+                s("Cost Data"),
+            ),
+            ("SUBSTITUTE(\"abc\",\"\",\"x\")", s("abc")),
+            ("REPLACE(\"abcdefghijk\",6,5,\"*\")", s("abcde*k")),
+            ("REPLACE(\"2009\",3,2,\"10\")", s("2010")),
+            ("REPLACE(\"abc\",5,1,\"x\")", s("abcx")),
+            ("REPT(\"*-\",3)", s("*-*-*-")),
+            ("REPT(\"ab\",2.9)", s("abab")),
+            (
+                "TRIM(\" First Quarter   Earnings \")",
+                s("First Quarter Earnings"),
+            ),
+            (
+                "CLEAN(CONCAT(CHAR(9),\"Monthly report\",CHAR(10)))",
+                s("Monthly report"),
+            ),
+            ("EXACT(\"word\",\"word\")", CellValue::Bool(true)),
+            ("EXACT(\"Word\",\"word\")", CellValue::Bool(false)),
+            ("TEXTJOIN(\", \",TRUE,A1:A3)", s("Alpha, Gamma")),
+            (
+                "TEXTJOIN(\", \",FALSE,A1:A3,\"Delta\")",
+                s("Alpha, , Gamma, Delta"),
+            ),
+            ("TEXTBEFORE(\"Red riding hood's\",\" \")", s("Red")),
+            (
+                "TEXTBEFORE(\"Red riding hood's\",\" \",-1)",
+                s("Red riding"),
+            ),
+            (
+                "TEXTAFTER(\"Red riding hood's\",\"RIDING\",1,1)",
+                s(" hood's"),
+            ),
+            ("TEXTAFTER(\"abc\",\"x\",1,0,FALSE,\"none\")", s("none")),
+            ("TEXTSPLIT(\"Dakota Lennon Sanchez\",\" \")", s("Dakota")),
+        ],
+    )
+    .await;
+
+    assert!(matches!(
+        eval(&engine, "TEXTBEFORE(\"abc\",\"x\")").await,
+        CellValue::Error(e) if e == "#N/A"
+    ));
+    assert!(matches!(
+        eval(&engine, "FIND(\"x\",\"abc\")").await,
+        CellValue::Error(_)
+    ));
+}
+
+#[tokio::test]
+async fn text_positions_count_utf16_code_units() {
+    let engine = TestEngine::new();
+
+    assert_table(
+        &engine,
+        &[
+            // CJK ideographs are one code unit each
+            ("LEN(\"東京都\")", n(3)),
+            ("LEFT(\"東京都\",2)", s("東京")),
+            ("MID(\"東京都庁\",2,2)", s("京都")),
+            ("FIND(\"都\",\"東京都\")", n(3)),
+            // Emoji outside the Basic Multilingual Plane are two
+            ("LEN(\"😀\")", n(2)),
+            ("LEN(\"a😀b\")", n(4)),
+            ("LEFT(\"😀ab\",2)", s("😀")),
+            ("RIGHT(\"ab😀\",3)", s("b😀")),
+            ("MID(\"a😀b\",4,1)", s("b")),
+            ("FIND(\"b\",\"a😀b\")", n(4)),
+            // `?` matches a whole character, reported at its first code unit
+            ("SEARCH(\"?b\",\"a😀b\")", n(2)),
+            ("REPLACE(\"a😀b\",2,2,\"-\")", s("a-b")),
+            ("SUBSTITUTE(\"😀-😀\",\"😀\",\"x\",2)", s("😀-x")),
+            ("TEXTAFTER(\"😀:東京\",\":\")", s("東京")),
+        ],
+    )
+    .await;
+}
+
+#[cfg(feature = "regex")]
+#[tokio::test]
+async fn regex_matches_libreoffice() {
+    let engine = TestEngine::new();
+
+    assert_table(
+        &engine,
+        &[
+            ("REGEX(\"Order 1234\",\"[0-9]+\")", s("1234")),
+            ("REGEX(\"a1b22c333\",\"[0-9]+\",,3)", s("333")),
+            ("REGEX(\"a1b22c333\",\"[0-9]+\",\"#\")", s("a#b22c333")),
+            ("REGEX(\"a1b22c333\",\"[0-9]+\",\"#\",\"g\")", s("a#b#c#")),
+            ("REGEX(\"a1b22c333\",\"[0-9]+\",\"#\",2)", s("a1b#c333")),
+            (
+                "REGEX(\"John Smith\",\"(\\w+) (\\w+)\",\"$2, $1\")",
+                s("Smith, John"),
+            ),
+            ("REGEX(\"東京 😀\",\"\\p{Han}+\")", s("東京")),
+        ],
+    )
+    .await;
+
+    assert!(matches!(
+        eval(&engine, "REGEX(\"abc\",\"[0-9]\")").await,
+        CellValue::Error(e) if e == "#N/A"
+    ));
+}
@@ -4,7 +4,7 @@ use crate::sheet::eval::engine::{
 use crate::sheet::eval::parser::Expr;
 use crate::sheet::{CellValue, Result};
 
-use super::helpers::{to_non_negative_int, to_positive_int};
+use super::helpers::{to_non_negative_int, to_positive_int, trim_spaces, utf16_len};
 
 pub(crate) async fn eval_len(
     ctx: EvalCtx<'_>,
@@ -16,7 +16,7 @@ pub(crate) async fn eval_len(
     }
     let v = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     let s = to_text(&v);
-    Ok(CellValue::Int(s.chars().count() as i64))
+    Ok(CellValue::Int(utf16_len(&s) as i64))
 }
 
 fn fullwidth_to_halfwidth(s: &str) -> String {
@@ -146,7 +146,7 @@ pub(crate) async fn eval_trim(
     }
     let v = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     let s = to_text(&v);
-    Ok(CellValue::String(s.trim().to_string()))
+    Ok(CellValue::String(trim_spaces(&s)))
 }
 
 pub(crate) async fn eval_concat(
@@ -223,7 +223,7 @@ pub(crate) async fn eval_rept(
     if s.is_empty() || times == 0 {
         return Ok(CellValue::String(String::new()));
     }
-    if s.chars().count().saturating_mul(times) > MAX_REPT_RESULT {
+    if utf16_len(&s).saturating_mul(times) > MAX_REPT_RESULT {
         return Ok(CellValue::Error(
             "REPT result exceeds maximum length of 32767 characters".to_string(),
         ));
@@ -391,13 +391,11 @@ pub(crate) async fn eval_substitute(
     let text = to_text(&text_val);
     let old_val = evaluate_expression(ctx, current_sheet, &args[1]).await?;
     let old_text = to_text(&old_val);
-    if old_text.is_empty() {
-        return Ok(CellValue::Error(
-            "SUBSTITUTE old_text must not be empty".to_string(),
-        ));
-    }
     let new_val = evaluate_expression(ctx, current_sheet, &args[2]).await?;
     let new_text = to_text(&new_val);
+    if old_text.is_empty() {
+        return Ok(CellValue::String(text));
+    }
     if args.len() == 4 {
         let instance_val = evaluate_expression(ctx, current_sheet, &args[3]).await?;
         let instance = match to_positive_int(&instance_val) {
@@ -650,10 +648,8 @@ mod tests {
         let ctx = engine.ctx();
         let args = vec![str_expr("hello"), str_expr(""), str_expr("x")];
         let result = eval_substitute(ctx, "Sheet1", &args).await.unwrap();
-        match result {
-            CellValue::Error(e) => assert!(e.contains("must not be empty")),
-            _ => panic!("Expected Error"),
-        }
+        // Excel leaves the text unchanged when there is nothing to replace
+        assert_eq!(result, CellValue::String("hello".to_string()));
     }
 
     #[tokio::test]
@@ -799,4 +795,50 @@ mod tests {
             _ => panic!("Expected empty String"),
         }
     }
+
+    #[tokio::test]
+    async fn test_eval_len_counts_utf16_units() {
+        let engine = crate::sheet::eval::engine::test_helpers::TestEngine::new();
+        let ctx = engine.ctx();
+        for (text, expected) in [("世界", 2), ("😀", 2), ("a👍🏽b", 6)] {
+            let args = vec![str_expr(text)];
+            let result = eval_len(ctx, "Sheet1", &args).await.unwrap();
+            assert_eq!(result, CellValue::Int(expected), "{text}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_eval_trim_collapses_inner_spaces() {
+        let engine = crate::sheet::eval::engine::test_helpers::TestEngine::new();
+        let ctx = engine.ctx();
+        let args = vec![str_expr("  First   Quarter  Earnings ")];
+        let result = eval_trim(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(
+            result,
+            CellValue::String("First Quarter Earnings".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_eval_textjoin_range() {
+        let engine = crate::sheet::eval::engine::test_helpers::TestEngine::new();
+        let ctx = engine.ctx();
+        engine.set_cell("Sheet1", 1, 1, CellValue::String("a".to_string()));
+        engine.set_cell("Sheet1", 1, 2, CellValue::Int(1));
+        engine.set_cell("Sheet1", 2, 2, CellValue::Bool(true));
+        let range = Expr::Range(crate::sheet::eval::parser::RangeRef {
+            sheet: "Sheet1".to_string(),
+            start_row: 1,
+            start_col: 1,
+            end_row: 2,
+            end_col: 2,
+        });
+        let args = vec![str_expr("-"), bool_expr(true), range.clone(), str_expr("z")];
+        let result = eval_textjoin(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::String("a-1-TRUE-z".to_string()));
+
+        let args = vec![str_expr("-"), bool_expr(false), range];
+        let result = eval_textjoin(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::String("a-1--TRUE".to_string()));
+    }
 }
@@ -21,28 +21,55 @@ pub(crate) fn to_positive_int(value: &CellValue) -> Option<usize> {
     })
 }
 
+// Text lengths and positions.
+//
+// Excel measures text in UTF-16 code units: a CJK ideograph counts as one
+// character, while an emoji outside the Basic Multilingual Plane counts as
+// two. LEN, LEFT, RIGHT, MID, REPLACE, FIND and SEARCH all use this unit so
+// that positions computed by one function line up with another. Rust strings
+// cannot hold half of a surrogate pair, so when a position splits one, the
+// half that is kept becomes U+FFFD.
+
+/// Length of `s` in UTF-16 code units.
+pub(crate) fn utf16_len(s: &str) -> usize {
+    s.encode_utf16().count()
+}
+
+/// UTF-16 offset of every character in `s`, followed by the total length.
+pub(crate) fn utf16_offsets(s: &str) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(s.len() + 1);
+    let mut offset = 0;
+    for ch in s.chars() {
+        offsets.push(offset);
+        offset += ch.len_utf16();
+    }
+    offsets.push(offset);
+    offsets
+}
+
+fn utf16_slice(units: &[u16], start: usize, end: usize) -> String {
+    let end = end.min(units.len());
+    let start = start.min(end);
+    String::from_utf16_lossy(&units[start..end])
+}
+
 pub(crate) fn take_left(s: &str, count: usize) -> String {
-    s.chars().take(count).collect()
+    let units: Vec<u16> = s.encode_utf16().collect();
+    utf16_slice(&units, 0, count)
 }
 
 pub(crate) fn take_right(s: &str, count: usize) -> String {
-    let chars: Vec<char> = s.chars().collect();
-    let len = chars.len();
-    let start = len.saturating_sub(count);
-    chars[start..].iter().collect()
+    let units: Vec<u16> = s.encode_utf16().collect();
+    utf16_slice(&units, units.len().saturating_sub(count), units.len())
 }
 
 pub(crate) fn take_mid(s: &str, start_num: usize, count: usize) -> String {
-    if count == 0 {
-        return String::new();
-    }
-    let chars: Vec<char> = s.chars().collect();
-    if start_num == 0 || start_num > chars.len() {
+    if start_num == 0 || count == 0 {
         return String::new();
     }
+    let units: Vec<u16> = s.encode_utf16().collect();
     let start_idx = start_num - 1;
-    let end_idx = (start_idx + count).min(chars.len());
-    chars[start_idx..end_idx].iter().collect()
+    utf16_slice(&units, start_idx, start_idx.saturating_add(count))
 }
 
 fn char_byte_width(ch: char) -> usize {
@@ -137,6 +164,8 @@ pub(crate) fn char_index_from_dbcs_byte(prefixes: &[usize], start_byte: usize) -
     (0..prefixes.len().saturating_sub(1)).find(|&i| prefixes[i + 1] > target)
 }
 
+/// Replaces `num_chars` UTF-16 code units starting at the 1-based
+/// `start_num`. A start past the end of the text appends `replacement`.
 pub(crate) fn replace_chars_segment(
     s: &str,
     start_num: usize,
@@ -146,20 +175,12 @@ pub(crate) fn replace_chars_segment(
     if start_num == 0 {
         return None;
     }
-    let chars: Vec<char> = s.chars().collect();
-    if start_num > chars.len() {
-        return None;
-    }
-    let start_idx = start_num - 1;
-    let end_idx = (start_idx + num_chars).min(chars.len());
-    let mut out = String::new();
-    for ch in &chars[..start_idx] {
-        out.push(*ch);
-    }
+    let units: Vec<u16> = s.encode_utf16().collect();
+    let start_idx = (start_num - 1).min(units.len());
+    let end_idx = start_idx.saturating_add(num_chars).min(units.len());
+    let mut out = utf16_slice(&units, 0, start_idx);
     out.push_str(replacement);
-    for ch in &chars[end_idx..] {
-        out.push(*ch);
-    }
+    out.push_str(&utf16_slice(&units, end_idx, units.len()));
     Some(out)
 }
 
@@ -215,6 +236,140 @@ pub(crate) fn replace_bytes_segment(
     Some(out)
 }
 
+/// Collapses runs of spaces to a single space and strips leading and
+/// trailing spaces, like Excel's TRIM. Only U+0020 counts as a space, so
+/// non-breaking spaces and tabs are kept.
+pub(crate) fn trim_spaces(s: &str) -> String {
+    s.split(' ')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Pieces of a wildcard pattern used by SEARCH.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum WildcardToken {
+    Char(char),
+    /// `?`, matching any single character
+    AnyChar,
+    /// `*`, matching any run of characters, including none
+    AnyRun,
+}
+
+/// Splits an Excel wildcard pattern into tokens.
+///
+/// `~` escapes a following `*`, `?` or `~`; before any other character, or
+/// at the end of the pattern, it is a literal tilde.
+pub(crate) fn parse_wildcard(pattern: &str) -> Vec<WildcardToken> {
+    let mut tokens = Vec::new();
+    let mut chars = pattern.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '~' => match chars.peek() {
+                Some(&next @ ('*' | '?' | '~')) => {
+                    chars.next();
+                    tokens.push(WildcardToken::Char(next));
+                },
+                _ => tokens.push(WildcardToken::Char('~')),
+            },
+            '*' => tokens.push(WildcardToken::AnyRun),
+            '?' => tokens.push(WildcardToken::AnyChar),
+            other => tokens.push(WildcardToken::Char(other)),
+        }
+    }
+    tokens
+}
+
+/// Whether `tokens` match some prefix of `text`.
+///
+/// Characters are compared with `eq`, so callers fold case beforehand when
+/// the match should ignore it.
+pub(crate) fn wildcard_matches_prefix(tokens: &[WildcardToken], text: &[char]) -> bool {
+    // reachable[j]: the tokens seen so far can consume exactly text[..j]
+    let mut reachable = vec![false; text.len() + 1];
+    reachable[0] = true;
+    for token in tokens {
+        let mut next = vec![false; text.len() + 1];
+        match token {
+            WildcardToken::AnyRun => {
+                if let Some(first) = reachable.iter().position(|&r| r) {
+                    next[first..].fill(true);
+                }
+            },
+            WildcardToken::AnyChar => {
+                next[1..].copy_from_slice(&reachable[..text.len()]);
+            },
+            WildcardToken::Char(c) => {
+                for j in 0..text.len() {
+                    next[j + 1] = reachable[j] && text[j] == *c;
+                }
+            },
+        }
+        if !next.contains(&true) {
+            return false;
+        }
+        reachable = next;
+    }
+    true
+}
+
+/// Lowercases a character when its lowercase form is a single character.
+///
+/// Keeping one character per character means positions found in folded
+/// text are also positions in the original.
+pub(crate) fn fold_char(ch: char) -> char {
+    let mut lower = ch.to_lowercase();
+    match (lower.next(), lower.next()) {
+        (Some(l), None) => l,
+        _ => ch,
+    }
+}
+
+/// End of the match of `needle` at byte `start` of `text`, if there is one.
+fn match_at(text: &str, start: usize, needle: &str, ignore_case: bool) -> Option<usize> {
+    let mut hay = text[start..].char_indices();
+    for n in needle.chars() {
+        let (_, h) = hay.next()?;
+        let equal = if ignore_case {
+            fold_char(h) == fold_char(n)
+        } else {
+            h == n
+        };
+        if !equal {
+            return None;
+        }
+    }
+    Some(hay.next().map_or(text.len(), |(i, _)| start + i))
+}
+
+/// Byte ranges of the non-overlapping occurrences of any of `delimiters`
+/// in `text`, from left to right.
+///
+/// Where several delimiters match at the same place, the first one listed
+/// wins. Empty delimiters are never found.
+pub(crate) fn find_delimiters(
+    text: &str,
+    delimiters: &[String],
+    ignore_case: bool,
+) -> Vec<(usize, usize)> {
+    let mut matches = Vec::new();
+    let mut search_from = 0;
+    for (start, _) in text.char_indices() {
+        if start < search_from {
+            continue;
+        }
+        let end = delimiters
+            .iter()
+            .filter(|d| !d.is_empty())
+            .find_map(|d| match_at(text, start, d, ignore_case));
+        if let Some(end) = end {
+            matches.push((start, end));
+            search_from = end;
+        }
+    }
+    matches
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -504,7 +659,11 @@ mod tests {
 
     #[test]
     fn test_replace_chars_segment_start_beyond() {
-        assert_eq!(replace_chars_segment("Hello", 10, 2, "XX"), None);
+        // Excel appends the replacement when start_num is past the end
+        assert_eq!(
+            replace_chars_segment("Hello", 10, 2, "XX"),
+            Some("HelloXX".to_string())
+        );
     }
 
     #[test]
@@ -566,4 +725,99 @@ mod tests {
         let result = replace_bytes_segment("A世C", 2, 1, "X");
         assert!(result.is_some());
     }
+
+    // ===== UTF-16 positions =====
+
+    #[test]
+    fn test_utf16_len_counts_surrogate_pairs() {
+        assert_eq!(utf16_len("世界"), 2);
+        assert_eq!(utf16_len("😀"), 2);
+        assert_eq!(utf16_len("a😀b"), 4);
+        assert_eq!(utf16_offsets("a😀b"), vec![0, 1, 3, 4]);
+    }
+
+    #[test]
+    fn test_take_with_emoji() {
+        assert_eq!(take_left("😀ab", 2), "😀");
+        assert_eq!(take_right("ab😀", 3), "b😀");
+        assert_eq!(take_mid("a😀b", 2, 2), "😀");
+        // Splitting a surrogate pair keeps U+FFFD in place of the lone half
+        assert_eq!(take_left("😀", 1), "\u{FFFD}");
+        assert_eq!(
+            replace_chars_segment("a😀b", 2, 2, "-"),
+            Some("a-b".to_string())
+        );
+    }
+
+    // ===== trim_spaces tests =====
+
+    #[test]
+    fn test_trim_spaces_collapses_runs() {
+        assert_eq!(trim_spaces("  a   b  c "), "a b c");
+        assert_eq!(trim_spaces("   "), "");
+        assert_eq!(trim_spaces("a\u{A0}\u{A0}b"), "a\u{A0}\u{A0}b");
+        assert_eq!(trim_spaces("\ta  b"), "\ta b");
+    }
+
+    // ===== wildcard tests =====
+
+    fn matches(pattern: &str, text: &str) -> bool {
+        let text: Vec<char> = text.chars().collect();
+        wildcard_matches_prefix(&parse_wildcard(pattern), &text)
+    }
+
+    #[test]
+    fn test_wildcard_matches_prefix() {
+        assert!(matches("a?c", "abcd"));
+        assert!(matches("a*d", "abcd"));
+        assert!(matches("*", ""));
+        assert!(matches("b*", "b"));
+        assert!(!matches("a?c", "ac"));
+        assert!(!matches("x*", "abc"));
+    }
+
+    #[test]
+    fn test_wildcard_escapes() {
+        assert_eq!(
+            parse_wildcard("~*~?~~~a"),
+            vec![
+                WildcardToken::Char('*'),
+                WildcardToken::Char('?'),
+                WildcardToken::Char('~'),
+                WildcardToken::Char('~'),
+                WildcardToken::Char('a'),
+            ]
+        );
+        assert!(matches("a~*", "a*b"));
+        assert!(!matches("a~*", "ab"));
+    }
+
+    // ===== find_delimiters tests =====
+
+    fn delims(list: &[&str]) -> Vec<String> {
+        list.iter().map(|d| d.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_delimiters() {
+        assert_eq!(
+            find_delimiters("a-b-c", &delims(&["-"]), false),
+            vec![(1, 2), (3, 4)]
+        );
+        assert_eq!(
+            find_delimiters("aaaa", &delims(&["aa"]), false),
+            vec![(0, 2), (2, 4)]
+        );
+        assert_eq!(find_delimiters("xAy", &delims(&["a"]), false), vec![]);
+        assert_eq!(find_delimiters("xAy", &delims(&["a"]), true), vec![(1, 2)]);
+        assert_eq!(
+            find_delimiters("世界世", &delims(&["世"]), false),
+            vec![(0, 3), (6, 9)]
+        );
+        assert_eq!(
+            find_delimiters("a, b;c", &delims(&[", ", ";"]), false),
+            vec![(1, 3), (4, 5)]
+        );
+        assert_eq!(find_delimiters("abc", &delims(&[""]), false), vec![]);
+    }
 }
@@ -4,6 +4,7 @@ mod formatting;
 mod helpers;
 mod modern;
 mod numbering;
+mod pattern;
 mod substring;
 mod unicode;
 
@@ -16,6 +17,7 @@ pub(crate) use modern::{
     eval_arraytotext, eval_textafter, eval_textbefore, eval_textsplit, eval_valuetotext,
 };
 pub(crate) use numbering::{eval_arabic, eval_roman};
+pub(crate) use pattern::eval_regex;
 pub(crate) use substring::{
     eval_char, eval_clean, eval_code, eval_find, eval_findb, eval_left, eval_leftb, eval_mid,
     eval_midb, eval_replace, eval_replaceb, eval_right, eval_rightb, eval_search, eval_searchb,
@@ -2,6 +2,8 @@ use crate::sheet::eval::engine::{EvalCtx, evaluate_expression, to_bool, to_text}
 use crate::sheet::eval::parser::Expr;
 use crate::sheet::{CellValue, Result};
 
+use super::helpers::{find_delimiters, utf16_len};
+
 pub(crate) async fn eval_valuetotext(
     ctx: EvalCtx<'_>,
     current_sheet: &str,
@@ -95,164 +97,113 @@ pub(crate) async fn eval_textbefore(
     current_sheet: &str,
     args: &[Expr],
 ) -> Result<CellValue> {
-    if args.len() < 2 || args.len() > 6 {
-        return Ok(CellValue::Error(
-            "TEXTBEFORE expects 2 to 6 arguments".to_string(),
-        ));
-    }
-    let text = to_text(&evaluate_expression(ctx, current_sheet, &args[0]).await?);
-    let delimiter = to_text(&evaluate_expression(ctx, current_sheet, &args[1]).await?);
-    if delimiter.is_empty() {
-        return Ok(CellValue::Error("#VALUE!".to_string()));
-    }
-
-    let instance_num = if args.len() >= 3 {
-        crate::sheet::eval::engine::to_number(
-            &evaluate_expression(ctx, current_sheet, &args[2]).await?,
-        )
-        .unwrap_or(1.0) as i32
-    } else {
-        1
-    };
-
-    let match_mode = if args.len() >= 4 {
-        crate::sheet::eval::engine::to_number(
-            &evaluate_expression(ctx, current_sheet, &args[3]).await?,
-        )
-        .unwrap_or(0.0) as i32
-    } else {
-        0
-    };
-
-    let _match_end = if args.len() >= 5 {
-        crate::sheet::eval::engine::to_number(
-            &evaluate_expression(ctx, current_sheet, &args[4]).await?,
-        )
-        .unwrap_or(0.0) as i32
-    } else {
-        0
-    };
-
-    let _if_not_found = if args.len() >= 6 {
-        Some(to_text(
-            &evaluate_expression(ctx, current_sheet, &args[5]).await?,
-        ))
-    } else {
-        None
-    };
-
-    let haystack = if match_mode == 1 {
-        text.to_lowercase()
-    } else {
-        text.clone()
-    };
-    let needle = if match_mode == 1 {
-        delimiter.to_lowercase()
-    } else {
-        delimiter.clone()
-    };
+    eval_text_around(ctx, current_sheet, args, true, "TEXTBEFORE").await
+}
 
-    let mut indices = Vec::new();
-    let mut start = 0;
-    while let Some(pos) = haystack[start..].find(&needle) {
-        indices.push(start + pos);
-        start += pos + needle.len();
-    }
+pub(crate) async fn eval_textafter(
+    ctx: EvalCtx<'_>,
+    current_sheet: &str,
+    args: &[Expr],
+) -> Result<CellValue> {
+    eval_text_around(ctx, current_sheet, args, false, "TEXTAFTER").await
+}
 
-    if indices.is_empty() {
-        return Ok(CellValue::Error("#N/A".to_string()));
+/// Evaluates an optional number argument, truncated to an integer.
+///
+/// Missing or omitted arguments give `default`, and non-numeric ones `None`.
+async fn int_arg(
+    ctx: EvalCtx<'_>,
+    current_sheet: &str,
+    args: &[Expr],
+    index: usize,
+    default: i64,
+) -> Result<Option<i64>> {
+    match args.get(index) {
+        None => Ok(Some(default)),
+        Some(arg) => {
+            let value = evaluate_expression(ctx, current_sheet, arg).await?;
+            if matches!(value, CellValue::Empty) {
+                return Ok(Some(default));
+            }
+            Ok(crate::sheet::eval::engine::to_number(&value).map(|n| n.trunc() as i64))
+        },
     }
-
-    let idx = if instance_num > 0 {
-        if instance_num as usize > indices.len() {
-            return Ok(CellValue::Error("#N/A".to_string()));
-        }
-        indices[instance_num as usize - 1]
-    } else if instance_num < 0 {
-        let abs_inst = instance_num.unsigned_abs() as usize;
-        if abs_inst > indices.len() {
-            return Ok(CellValue::Error("#N/A".to_string()));
-        }
-        indices[indices.len() - abs_inst]
-    } else {
-        return Ok(CellValue::Error("#VALUE!".to_string()));
-    };
-
-    Ok(CellValue::String(text[..idx].to_string()))
 }
 
-pub(crate) async fn eval_textafter(
+/// TEXTBEFORE and TEXTAFTER: the text before or after the `instance_num`th
+/// delimiter, counting from the end when `instance_num` is negative.
+///
+/// The delimiter may be a range of alternatives. An empty delimiter matches
+/// at the start of the text, or at its end when counting from the end.
+async fn eval_text_around(
     ctx: EvalCtx<'_>,
     current_sheet: &str,
     args: &[Expr],
+    before: bool,
+    name: &str,
 ) -> Result<CellValue> {
     if args.len() < 2 || args.len() > 6 {
-        return Ok(CellValue::Error(
-            "TEXTAFTER expects 2 to 6 arguments".to_string(),
-        ));
+        return Ok(CellValue::Error(format!("{name} expects 2 to 6 arguments")));
     }
     let text = to_text(&evaluate_expression(ctx, current_sheet, &args[0]).await?);
-    let delimiter = to_text(&evaluate_expression(ctx, current_sheet, &args[1]).await?);
-    if delimiter.is_empty() {
+    let mut delimiters = Vec::new();
+    crate::sheet::eval::engine::for_each_value_in_expr(ctx, current_sheet, &args[1], |v| {
+        delimiters.push(to_text(v));
+        Ok(())
+    })
+    .await?;
+
+    let Some(instance_num) = int_arg(ctx, current_sheet, args, 2, 1).await? else {
+        return Ok(CellValue::Error("#VALUE!".to_string()));
+    };
+    if instance_num == 0 || instance_num.unsigned_abs() as usize > utf16_len(&text).max(1) {
         return Ok(CellValue::Error("#VALUE!".to_string()));
     }
-
-    let instance_num = if args.len() >= 3 {
-        crate::sheet::eval::engine::to_number(
-            &evaluate_expression(ctx, current_sheet, &args[2]).await?,
-        )
-        .unwrap_or(1.0) as i32
-    } else {
-        1
+    let ignore_case = match int_arg(ctx, current_sheet, args, 3, 0).await? {
+        Some(0) => false,
+        Some(1) => true,
+        _ => return Ok(CellValue::Error("#VALUE!".to_string())),
     };
-
-    let match_mode = if args.len() >= 4 {
-        crate::sheet::eval::engine::to_number(
-            &evaluate_expression(ctx, current_sheet, &args[3]).await?,
-        )
-        .unwrap_or(0.0) as i32
-    } else {
-        0
+    let match_end = match args.get(4) {
+        Some(arg) => to_bool(&evaluate_expression(ctx, current_sheet, arg).await?),
+        None => false,
     };
 
-    let haystack = if match_mode == 1 {
-        text.to_lowercase()
-    } else {
-        text.clone()
-    };
-    let needle = if match_mode == 1 {
-        delimiter.to_lowercase()
+    let mut matches = if delimiters.iter().all(|d| d.is_empty()) {
+        if instance_num > 0 {
+            vec![(0, 0)]
+        } else {
+            vec![(text.len(), text.len())]
+        }
     } else {
-        delimiter.clone()
+        find_delimiters(&text, &delimiters, ignore_case)
     };
-
-    let mut indices = Vec::new();
-    let mut start = 0;
-    while let Some(pos) = haystack[start..].find(&needle) {
-        indices.push(start + pos);
-        start += pos + needle.len();
-    }
-
-    if indices.is_empty() {
-        return Ok(CellValue::Error("#N/A".to_string()));
+    if match_end {
+        if instance_num > 0 {
+            matches.push((text.len(), text.len()));
+        } else {
+            matches.insert(0, (0, 0));
+        }
     }
 
-    let idx = if instance_num > 0 {
-        if instance_num as usize > indices.len() {
-            return Ok(CellValue::Error("#N/A".to_string()));
-        }
-        indices[instance_num as usize - 1]
-    } else if instance_num < 0 {
-        let abs_inst = instance_num.unsigned_abs() as usize;
-        if abs_inst > indices.len() {
-            return Ok(CellValue::Error("#N/A".to_string()));
-        }
-        indices[indices.len() - abs_inst]
+    let index = if instance_num > 0 {
+        Some(instance_num as usize - 1)
     } else {
-        return Ok(CellValue::Error("#VALUE!".to_string()));
+        matches
+            .len()
+            .checked_sub(instance_num.unsigned_abs() as usize)
     };
-
-    Ok(CellValue::String(text[idx + delimiter.len()..].to_string()))
+    match index.and_then(|i| matches.get(i)) {
+        Some(&(start, end)) => Ok(CellValue::String(if before {
+            text[..start].to_string()
+        } else {
+            text[end..].to_string()
+        })),
+        None => match args.get(5) {
+            Some(arg) => evaluate_expression(ctx, current_sheet, arg).await,
+            None => Ok(CellValue::Error("#N/A".to_string())),
+        },
+    }
 }
 
 pub(crate) async fn eval_textsplit(
@@ -297,54 +248,34 @@ pub(crate) async fn eval_textsplit(
         false
     };
 
-    let match_mode = if args.len() >= 5 {
-        match crate::sheet::eval::engine::to_number(
-            &evaluate_expression(ctx, current_sheet, &args[4]).await?,
-        ) {
-            Some(n) => n.trunc() as i32,
-            None => 0,
-        }
-    } else {
-        0
+    let ignore_case = match int_arg(ctx, current_sheet, args, 4, 0).await? {
+        Some(0) => false,
+        Some(1) => true,
+        _ => return Ok(CellValue::Error("#VALUE!".to_string())),
     };
 
-    // Very simplified implementation of TEXTSPLIT for now:
-    // It should ideally return an array/dynamic spill, but we'll return a joined string
-    // or just the first part for now until dynamic arrays are fully supported in eval.
-    // Excel returns an array. If we can't return an array yet, we'll return #VALUE! or the first result.
-
-    let mut all_delimiters = col_delimiters;
-    all_delimiters.extend(row_delimiters);
+    // TEXTSPLIT spills a two-dimensional array in Excel. Until the evaluator
+    // can return arrays, this gives the top-left cell of that array: the text
+    // up to the first column or row delimiter, skipping empty pieces when
+    // ignore_empty is set.
+    let mut delimiters = col_delimiters;
+    delimiters.extend(row_delimiters);
 
-    if all_delimiters.is_empty() {
-        return Ok(CellValue::String(text));
-    }
-
-    let mut parts = vec![text];
-    for delim in all_delimiters {
-        let mut new_parts = Vec::new();
-        for part in parts {
-            if match_mode == 1 {
-                // Case-insensitive split is harder with standard split
-                // Simplified: just use standard split for now
-                new_parts.extend(part.split(&delim).map(|s| s.to_string()));
-            } else {
-                new_parts.extend(part.split(&delim).map(|s| s.to_string()));
-            }
-        }
-        parts = new_parts;
+    let mut parts = Vec::new();
+    let mut last = 0;
+    for (start, end) in find_delimiters(&text, &delimiters, ignore_case) {
+        parts.push(&text[last..start]);
+        last = end;
     }
+    parts.push(&text[last..]);
 
     if ignore_empty {
         parts.retain(|s| !s.is_empty());
     }
 
-    // Since we don't have full dynamic array support in the current eval context return,
-    // we'll return the first part or a comma-separated list as a fallback.
-    if parts.is_empty() {
-        Ok(CellValue::Error("#N/A".to_string()))
-    } else {
-        Ok(CellValue::String(parts[0].clone()))
+    match parts.first() {
+        Some(part) => Ok(CellValue::String(part.to_string())),
+        None => Ok(CellValue::Error("#N/A".to_string())),
     }
 }
 
@@ -510,4 +441,83 @@ mod tests {
         // Empty delimiter returns original text
         assert_eq!(result, CellValue::String("abc".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_eval_textbefore_textafter_options() {
+        let engine = TestEngine::new();
+        let ctx = engine.ctx();
+        let int = |n: i64| Expr::Literal(CellValue::Int(n));
+        let text = "Red riding hood's, red hood";
+        let cases = [
+            // Negative instances count from the end
+            (
+                true,
+                vec![str_expr(text), str_expr("hood"), int(-1)],
+                "Red riding hood's, red ",
+            ),
+            (
+                false,
+                vec![str_expr(text), str_expr("red"), int(1), int(1)],
+                " riding hood's, red hood",
+            ),
+            (
+                false,
+                vec![str_expr(text), str_expr("RED"), int(2), int(1)],
+                " hood",
+            ),
+            // match_end treats the end of the text as a delimiter
+            (
+                true,
+                vec![
+                    str_expr(text),
+                    str_expr("hood"),
+                    int(3),
+                    int(0),
+                    Expr::Literal(CellValue::Bool(true)),
+                ],
+                text,
+            ),
+            // An empty delimiter matches immediately
+            (false, vec![str_expr("abc"), str_expr("")], "abc"),
+            (true, vec![str_expr("abc"), str_expr(""), int(-1)], "abc"),
+        ];
+        for (before, args, expected) in cases {
+            let result = if before {
+                eval_textbefore(ctx, "Sheet1", &args).await.unwrap()
+            } else {
+                eval_textafter(ctx, "Sheet1", &args).await.unwrap()
+            };
+            assert_eq!(result, CellValue::String(expected.to_string()), "{args:?}");
+        }
+
+        let args = vec![
+            str_expr("abc"),
+            str_expr("-"),
+            int(1),
+            int(0),
+            Expr::Literal(CellValue::Bool(false)),
+            str_expr("none"),
+        ];
+        let result = eval_textafter(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::String("none".to_string()));
+
+        let args = vec![str_expr("abc"), str_expr("b"), int(0)];
+        let result = eval_textafter(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::Error("#VALUE!".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_eval_textsplit_options() {
+        let engine = TestEngine::new();
+        let ctx = engine.ctx();
+        let args = vec![
+            str_expr("--aXb"),
+            str_expr("-"),
+            str_expr("x"),
+            Expr::Literal(CellValue::Bool(true)),
+            Expr::Literal(CellValue::Int(1)),
+        ];
+        let result = eval_textsplit(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::String("a".to_string()));
+    }
 }
@@ -0,0 +1,275 @@
+use crate::sheet::eval::engine::EvalCtx;
+#[cfg(feature = "regex")]
+use crate::sheet::eval::engine::{evaluate_expression, to_text};
+use crate::sheet::eval::parser::Expr;
+use crate::sheet::{CellValue, Result};
+
+#[cfg(feature = "regex")]
+use super::helpers::to_positive_int;
+
+/// LibreOffice's `REGEX(text, expression, [replacement], [flags|occurrence])`.
+///
+/// Without a replacement, returns the match of `expression` in `text`, or
+/// `#N/A` when there is none. With a replacement, returns `text` with the
+/// match replaced; `$0`, `$1`, ... in the replacement insert capture groups
+/// and `\$` a literal dollar sign. The last argument is either the flag `"g"`,
+/// which replaces every match, or the 1-based occurrence to extract or
+/// replace. Expressions use the syntax of the `regex` crate, which agrees with
+/// LibreOffice's ICU patterns for the common constructs, including the `(?i)`
+/// flag for ignoring case.
+///
+/// Requires the `regex` feature; without it, REGEX evaluates to `#NAME?`.
+pub(crate) async fn eval_regex(
+    ctx: EvalCtx<'_>,
+    current_sheet: &str,
+    args: &[Expr],
+) -> Result<CellValue> {
+    #[cfg(feature = "regex")]
+    {
+        if args.len() < 2 || args.len() > 4 {
+            return Ok(CellValue::Error(
+                "REGEX expects 2 to 4 arguments (text, expression, [replacement], [flags|occurrence])"
+                    .to_string(),
+            ));
+        }
+        let text = to_text(&evaluate_expression(ctx, current_sheet, &args[0]).await?);
+        let expression = to_text(&evaluate_expression(ctx, current_sheet, &args[1]).await?);
+        let replacement = match args.get(2) {
+            Some(arg) => match evaluate_expression(ctx, current_sheet, arg).await? {
+                CellValue::Empty => None,
+                value => Some(to_text(&value)),
+            },
+            None => None,
+        };
+
+        let mut global = false;
+        let mut occurrence = 1;
+        if let Some(arg) = args.get(3) {
+            match evaluate_expression(ctx, current_sheet, arg).await? {
+                CellValue::Empty => {},
+                CellValue::String(flags) => {
+                    if flags != "g" {
+                        return Ok(CellValue::Error("#VALUE!".to_string()));
+                    }
+                    global = true;
+                },
+                value => match to_positive_int(&value) {
+                    Some(n) => occurrence = n,
+                    None => return Ok(CellValue::Error("#VALUE!".to_string())),
+                },
+            }
+        }
+
+        let Ok(re) = regex::Regex::new(&expression) else {
+            return Ok(CellValue::Error("#VALUE!".to_string()));
+        };
+
+        let Some(replacement) = replacement else {
+            return Ok(match re.find_iter(&text).nth(occurrence - 1) {
+                Some(m) => CellValue::String(m.as_str().to_string()),
+                None => CellValue::Error("#N/A".to_string()),
+            });
+        };
+        let replacement = to_rust_replacement(&replacement, re.captures_len());
+        if global {
+            return Ok(CellValue::String(
+                re.replace_all(&text, replacement.as_str()).into_owned(),
+            ));
+        }
+        let Some(caps) = re.captures_iter(&text).nth(occurrence - 1) else {
+            return Ok(CellValue::String(text));
+        };
+        let whole = caps.get(0).expect("group 0 always participates");
+        let mut out = text[..whole.start()].to_string();
+        caps.expand(&replacement, &mut out);
+        out.push_str(&text[whole.end()..]);
+        Ok(CellValue::String(out))
+    }
+    #[cfg(not(feature = "regex"))]
+    {
+        let _ = (ctx, current_sheet, args);
+        Ok(CellValue::Error("#NAME?".to_string()))
+    }
+}
+
+/// Rewrites an ICU replacement string, as LibreOffice uses, into the syntax of
+/// the `regex` crate.
+///
+/// ICU reads `$12` as group 12 only when the pattern has that many groups,
+/// and otherwise as group 1 followed by `2`; `\` escapes the next character.
+#[cfg(feature = "regex")]
+fn to_rust_replacement(replacement: &str, group_count: usize) -> String {
+    let chars: Vec<char> = replacement.chars().collect();
+    let mut out = String::with_capacity(replacement.len());
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                match chars[i + 1] {
+                    '$' => out.push_str("$$"),
+                    c => out.push(c),
+                }
+                i += 2;
+            },
+            '$' if chars.get(i + 1).is_some_and(char::is_ascii_digit) => {
+                let mut group = chars[i + 1].to_digit(10).unwrap_or(0) as usize;
+                i += 2;
+                while let Some(digit) = chars.get(i).and_then(|c| c.to_digit(10)) {
+                    let longer = group * 10 + digit as usize;
+                    if longer >= group_count {
+                        break;
+                    }
+                    group = longer;
+                    i += 1;
+                }
+                out.push_str(&format!("${{{group}}}"));
+            },
+            '$' if chars.get(i + 1) == Some(&'{') => {
+                // Named group, which both syntaxes write as ${name}
+                let end = chars[i..]
+                    .iter()
+                    .position(|&c| c == '}')
+                    .map_or(chars.len(), |p| i + p + 1);
+                out.extend(&chars[i..end]);
+                i = end;
+            },
+            '$' => {
+                out.push_str("$$");
+                i += 1;
+            },
+            c => {
+                out.push(c);
+                i += 1;
+            },
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sheet::eval::engine::test_helpers::TestEngine;
+
+    fn str_expr(s: &str) -> Expr {
+        Expr::Literal(CellValue::String(s.to_string()))
+    }
+
+    #[cfg(not(feature = "regex"))]
+    #[tokio::test]
+    async fn test_regex_without_feature() {
+        let engine = TestEngine::new();
+        let ctx = engine.ctx();
+        let args = vec![str_expr("abc"), str_expr("b")];
+        let result = eval_regex(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::Error("#NAME?".to_string()));
+    }
+
+    #[cfg(feature = "regex")]
+    mod with_feature {
+        use super::*;
+
+        async fn regex(args: &[Expr]) -> CellValue {
+            let engine = TestEngine::new();
+            eval_regex(engine.ctx(), "Sheet1", args).await.unwrap()
+        }
+
+        fn string(s: &str) -> CellValue {
+            CellValue::String(s.to_string())
+        }
+
+        #[tokio::test]
+        async fn test_regex_extract() {
+            let int = |n| Expr::Literal(CellValue::Int(n));
+            assert_eq!(
+                regex(&[str_expr("item 12, item 345"), str_expr(r"\d+")]).await,
+                string("12")
+            );
+            assert_eq!(
+                regex(&[
+                    str_expr("item 12, item 345"),
+                    str_expr(r"\d+"),
+                    Expr::Literal(CellValue::Empty),
+                    int(2),
+                ])
+                .await,
+                string("345")
+            );
+            assert_eq!(
+                regex(&[str_expr("abc"), str_expr(r"\d")]).await,
+                CellValue::Error("#N/A".to_string())
+            );
+            assert_eq!(
+                regex(&[str_expr("ABC"), str_expr("(?i)b")]).await,
+                string("B")
+            );
+        }
+
+        #[tokio::test]
+        async fn test_regex_replace() {
+            let text = "2024-01-15 and 2025-02-16";
+            let pattern = r"(\d+)-(\d+)-(\d+)";
+            assert_eq!(
+                regex(&[str_expr(text), str_expr(pattern), str_expr("$3/$2/$1")]).await,
+                string("15/01/2024 and 2025-02-16")
+            );
+            assert_eq!(
+                regex(&[
+                    str_expr(text),
+                    str_expr(pattern),
+                    str_expr("$3/$2/$1"),
+                    str_expr("g"),
+                ])
+                .await,
+                string("15/01/2024 and 16/02/2025")
+            );
+            assert_eq!(
+                regex(&[
+                    str_expr(text),
+                    str_expr(pattern),
+                    str_expr("<$0>"),
+                    Expr::Literal(CellValue::Int(2)),
+                ])
+                .await,
+                string("2024-01-15 and <2025-02-16>")
+            );
+            // An empty replacement deletes the match
+            assert_eq!(
+                regex(&[
+                    str_expr("a1b2"),
+                    str_expr(r"\d"),
+                    str_expr(""),
+                    str_expr("g")
+                ])
+                .await,
+                string("ab")
+            );
+            // No match leaves the text unchanged
+            assert_eq!(
+                regex(&[str_expr("abc"), str_expr(r"\d"), str_expr("x")]).await,
+                string("abc")
+            );
+        }
+
+        #[tokio::test]
+        async fn test_regex_errors() {
+            assert_eq!(
+                regex(&[str_expr("abc"), str_expr("(")]).await,
+                CellValue::Error("#VALUE!".to_string())
+            );
+            assert_eq!(
+                regex(&[str_expr("abc"), str_expr("b"), str_expr("x"), str_expr("i")]).await,
+                CellValue::Error("#VALUE!".to_string())
+            );
+        }
+
+        #[test]
+        fn test_to_rust_replacement() {
+            assert_eq!(to_rust_replacement("$1a", 2), "${1}a");
+            assert_eq!(to_rust_replacement("$12", 2), "${1}2");
+            assert_eq!(to_rust_replacement("$12", 13), "${12}");
+            assert_eq!(to_rust_replacement(r"\$1 costs $", 2), "$$1 costs $$");
+            assert_eq!(to_rust_replacement("${name}!", 2), "${name}!");
+        }
+    }
+}
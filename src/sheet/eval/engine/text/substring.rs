@@ -3,9 +3,10 @@ use crate::sheet::eval::parser::Expr;
 use crate::sheet::{CellValue, Result};
 
 use super::helpers::{
-    char_index_from_dbcs_byte, dbcs_byte_prefixes, replace_bytes_segment, replace_chars_segment,
-    take_left, take_left_bytes, take_mid, take_mid_bytes, take_right, take_right_bytes,
-    to_non_negative_int, to_positive_int,
+    WildcardToken, char_index_from_dbcs_byte, dbcs_byte_prefixes, fold_char, parse_wildcard,
+    replace_bytes_segment, replace_chars_segment, take_left, take_left_bytes, take_mid,
+    take_mid_bytes, take_right, take_right_bytes, to_non_negative_int, to_positive_int,
+    utf16_offsets, wildcard_matches_prefix,
 };
 
 pub(crate) async fn eval_char(
@@ -282,10 +283,6 @@ pub(crate) async fn eval_replace(
     let new_text = evaluate_expression(ctx, current_sheet, &args[3]).await?;
     let replacement = to_text(&new_text);
 
-    if start_num > original.chars().count() {
-        return Ok(CellValue::String(original));
-    }
-
     let replaced = replace_chars_segment(&original, start_num, num_chars, &replacement)
         .unwrap_or_else(|| original.clone());
     Ok(CellValue::String(replaced))
@@ -497,6 +494,11 @@ pub(crate) async fn eval_searchb(
     ))
 }
 
+/// FIND and SEARCH.
+///
+/// Positions count UTF-16 code units, as described in [`super::helpers`].
+/// SEARCH ignores case and supports the `*`, `?` and `~` wildcards; FIND
+/// matches the text exactly.
 async fn eval_find_like(
     ctx: EvalCtx<'_>,
     current_sheet: &str,
@@ -511,11 +513,6 @@ async fn eval_find_like(
     }
     let find_text = evaluate_expression(ctx, current_sheet, &args[0]).await?;
     let needle = to_text(&find_text);
-    if needle.is_empty() {
-        return Ok(CellValue::Error(format!(
-            "{name} requires non-empty find_text"
-        )));
-    }
     let within_text = evaluate_expression(ctx, current_sheet, &args[1]).await?;
     let haystack = to_text(&within_text);
     let start_num = if args.len() == 3 {
@@ -531,39 +528,38 @@ async fn eval_find_like(
     } else {
         1
     };
-    let hay_chars: Vec<(usize, char)> = haystack.char_indices().collect();
-    let hay_len = hay_chars.len();
+    let offsets = utf16_offsets(&haystack);
+    let hay_len = offsets[offsets.len() - 1];
     if start_num == 0 || start_num > hay_len.saturating_add(1) {
         return Ok(CellValue::Error(format!(
             "{name} start_num is out of range"
         )));
     }
-    let start_idx = start_num.saturating_sub(1);
-    let needle_char_len = needle.chars().count();
-    if needle_char_len == 0 {
+    // An empty find_text matches at start_num
+    if needle.is_empty() {
         return Ok(CellValue::Int(start_num as i64));
     }
-    if hay_len < needle_char_len || start_idx >= hay_len {
-        return Ok(CellValue::Error(format!("{name} could not find the text")));
-    }
-    for i in start_idx..=hay_len - needle_char_len {
-        let start_byte = hay_chars[i].0;
-        let end_byte = if i + needle_char_len < hay_len {
-            hay_chars[i + needle_char_len].0
-        } else {
-            haystack.len()
-        };
-        let candidate = &haystack[start_byte..end_byte];
-        let matched = if case_sensitive {
-            candidate == needle
-        } else {
-            candidate.to_lowercase() == needle.to_lowercase()
-        };
-        if matched {
-            return Ok(CellValue::Int((i + 1) as i64));
-        }
+
+    let fold = |c: char| if case_sensitive { c } else { fold_char(c) };
+    let hay_chars: Vec<char> = haystack.chars().map(fold).collect();
+    let start_idx = offsets.partition_point(|&offset| offset < start_num - 1);
+    let found = if case_sensitive {
+        let needle_chars: Vec<char> = needle.chars().collect();
+        (start_idx..hay_chars.len()).find(|&i| hay_chars[i..].starts_with(&needle_chars))
+    } else {
+        let tokens: Vec<WildcardToken> = parse_wildcard(&needle)
+            .into_iter()
+            .map(|token| match token {
+                WildcardToken::Char(c) => WildcardToken::Char(fold(c)),
+                other => other,
+            })
+            .collect();
+        (start_idx..hay_chars.len()).find(|&i| wildcard_matches_prefix(&tokens, &hay_chars[i..]))
+    };
+    match found {
+        Some(i) => Ok(CellValue::Int((offsets[i] + 1) as i64)),
+        None => Ok(CellValue::Error(format!("{name} could not find the text"))),
     }
-    Ok(CellValue::Error(format!("{name} could not find the text")))
 }
 
 #[cfg(test)]
@@ -767,4 +763,49 @@ mod tests {
             _ => panic!("Expected Int(7) - SEARCH is case insensitive"),
         }
     }
+
+    #[tokio::test]
+    async fn test_eval_left_truncates_num_chars() {
+        let engine = crate::sheet::eval::engine::test_helpers::TestEngine::new();
+        let ctx = engine.ctx();
+        let args = vec![str_expr("hello"), num_expr(2.9)];
+        let result = eval_left(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::String("he".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_eval_search_wildcards() {
+        let engine = crate::sheet::eval::engine::test_helpers::TestEngine::new();
+        let ctx = engine.ctx();
+        for (pattern, expected) in [("w?r", 8), ("l*d", 3), ("~?", 6), ("*", 1)] {
+            let args = vec![str_expr(pattern), str_expr("hello? world")];
+            let result = eval_search(ctx, "Sheet1", &args).await.unwrap();
+            assert_eq!(result, CellValue::Int(expected), "pattern {pattern}");
+        }
+        // FIND takes wildcard characters literally
+        let args = vec![str_expr("w?r"), str_expr("hello world")];
+        let result = eval_find(ctx, "Sheet1", &args).await.unwrap();
+        assert!(matches!(result, CellValue::Error(_)));
+    }
+
+    #[tokio::test]
+    async fn test_eval_find_empty_needle_returns_start() {
+        let engine = crate::sheet::eval::engine::test_helpers::TestEngine::new();
+        let ctx = engine.ctx();
+        let args = vec![str_expr(""), str_expr("hello"), num_expr(3.0)];
+        let result = eval_find(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::Int(3));
+    }
+
+    #[tokio::test]
+    async fn test_eval_find_counts_utf16_units() {
+        let engine = crate::sheet::eval::engine::test_helpers::TestEngine::new();
+        let ctx = engine.ctx();
+        let args = vec![str_expr("b"), str_expr("😀世b")];
+        let result = eval_find(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::Int(4));
+        let args = vec![str_expr("b"), str_expr("😀世b"), num_expr(2.0)];
+        let result = eval_search(ctx, "Sheet1", &args).await.unwrap();
+        assert_eq!(result, CellValue::Int(4));
+    }
 }
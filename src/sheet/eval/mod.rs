@@ -5,9 +5,14 @@
 //! it prefers using cached values embedded in files and can be extended
 //! over time to support more Excel semantics.
 
+pub mod dependencies;
 pub mod engine;
 pub mod parser;
 
+pub use self::dependencies::{
+    CellId, CircularReference, DependencyGraph, DependencyGraphBuilder, FormulaCell, Precedent,
+};
+
 use self::engine::{ReferenceResolver, ResolvedName};
 use self::parser::{RangeRef, parse_range_reference, parse_single_cell_reference};
 use crate::sheet::{CellValue, Result, WorkbookTrait};
@@ -18,7 +18,7 @@ pub enum BinaryOp {
 }
 
 /// A rectangular cell range reference.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RangeRef {
     pub sheet: String,
     pub start_row: u32,
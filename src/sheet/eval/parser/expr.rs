@@ -1,5 +1,7 @@
 //! Expression parsing (tokens + recursive descent parser).
 
+use crate::sheet::CellValue;
+
 use super::ast::{BinaryOp, Expr};
 use super::literal::parse_literal;
 use super::reference::{parse_range_reference, parse_single_cell_reference};
@@ -156,10 +158,18 @@ fn tokenize(input: &str) -> Option<Vec<Token>> {
             },
             _ => {
                 // Collect an atom until we hit whitespace or an operator/paren.
+                // Quoted sheet names such as 'My Sheet'!A1 may contain both.
                 let mut buf = String::new();
+                let mut in_quote = false;
                 while let Some(c) = chars.peek().copied() {
-                    if c.is_whitespace()
-                        || matches!(c, '+' | '-' | '*' | '/' | '(' | ')' | ',' | '<' | '>' | '=')
+                    if c == '\'' {
+                        in_quote = !in_quote;
+                    } else if !in_quote
+                        && (c.is_whitespace()
+                            || matches!(
+                                c,
+                                '+' | '-' | '*' | '/' | '(' | ')' | ',' | '<' | '>' | '='
+                            ))
                     {
                         break;
                     }
@@ -368,8 +378,12 @@ impl<'a> ExprParser<'a> {
             // Handle empty argument list: NAME()
             if !matches!(self.peek(), Some(Token::RParen)) {
                 loop {
-                    let expr = self.parse_expr()?;
-                    args.push(expr);
+                    // An omitted argument, as in NAME(a,,b), is an empty value
+                    if matches!(self.peek(), Some(Token::Comma | Token::RParen)) {
+                        args.push(Expr::Literal(CellValue::Empty));
+                    } else {
+                        args.push(self.parse_expr()?);
+                    }
                     match self.peek() {
                         Some(Token::Comma) => {
                             self.next();
@@ -416,4 +430,14 @@ mod tests {
             tokenize("SUMXMY2(A1:A2,B1:B2)")
         );
     }
+
+    #[test]
+    fn parses_quoted_sheet_names() {
+        let expr = parse_expression("Sheet1", "'My Sheet'!A1+'Q1-Q2'!B2:B3");
+        assert!(
+            expr.is_some(),
+            "parser failed on quoted sheet names; tokens: {:?}",
+            tokenize("'My Sheet'!A1+'Q1-Q2'!B2:B3")
+        );
+    }
 }
@@ -150,6 +150,31 @@ fn parse_a1_ref(cell: &str) -> Option<(u32, u32)> {
     Some((row, col))
 }
 
+/// Quote a sheet name for use in a reference, if it needs quoting.
+pub(crate) fn quote_sheet_name(sheet: &str) -> String {
+    if !sheet.is_empty()
+        && sheet
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '.')
+    {
+        sheet.to_string()
+    } else {
+        format!("'{}'", sheet.replace('\'', "''"))
+    }
+}
+
+/// Convert a 1-based column index to its letters (1 -> `A`, 27 -> `AA`).
+pub(crate) fn column_index_to_letters(mut col: u32) -> String {
+    let mut letters = Vec::new();
+    while col > 0 {
+        col -= 1;
+        letters.push(b'A' + (col % 26) as u8);
+        col /= 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).unwrap_or_default()
+}
+
 fn column_letters_to_index(col: &str) -> Option<u32> {
     let mut result: u32 = 0;
     for ch in col.chars() {
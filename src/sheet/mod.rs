@@ -44,9 +44,13 @@
 //! - **Trait-based API**: `Workbook`, `Worksheet`, `Cell` traits for advanced use
 
 // Submodule declarations
+#[cfg(feature = "ooxml")]
+mod builder;
+pub mod date_system;
 #[cfg(feature = "eval_engine")]
 pub mod eval;
 pub mod functions;
+pub mod row;
 pub mod text;
 pub mod traits;
 pub mod types;
@@ -54,9 +58,13 @@ mod workbook;
 mod workbook_types;
 
 // Re-exports
+#[cfg(feature = "ooxml")]
+pub use builder::{WorkbookBuilder, WorksheetBuilder};
+pub use date_system::DateSystem;
 #[cfg(feature = "eval_engine")]
-pub use eval::FormulaEvaluator;
+pub use eval::{DependencyGraph, FormulaEvaluator};
 pub use functions::*;
+pub use row::{Row, RowValue};
 pub use traits::{Cell, CellIterator, RowIterator, WorkbookTrait, Worksheet, WorksheetIterator};
 pub use types::{CellValue, Result};
 pub use workbook::Workbook;
@@ -0,0 +1,154 @@
+//! Borrowed row views produced by streaming row readers.
+//!
+//! [`Row`] is what the lazy row iterators (`Worksheet::rows_iter` for XLSX,
+//! `XlsbWorkbook::rows_iter` for XLSB) yield. Cells are stored sparsely as
+//! `(column, value)` pairs, and string values borrow from the workbook's
+//! shared strings table (or the worksheet part) instead of being cloned.
+
+use std::borrow::Cow;
+
+use super::DateSystem;
+use super::types::CellValue;
+
+/// A cell value that may borrow its text from the source workbook.
+///
+/// This mirrors [`CellValue`]; use [`RowValue::into_owned`] to convert.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowValue<'a> {
+    /// Empty cell
+    Empty,
+    /// Boolean value
+    Bool(bool),
+    /// 64-bit signed integer
+    Int(i64),
+    /// 64-bit floating point number
+    Float(f64),
+    /// String value, borrowed from the shared strings table when possible
+    String(Cow<'a, str>),
+    /// Date/time value (stored as serial number)
+    DateTime(f64),
+    /// Error value
+    Error(Cow<'a, str>),
+    /// Formula with optional cached result
+    Formula {
+        /// Formula expression (without leading '=')
+        formula: Cow<'a, str>,
+        /// Optional cached result value
+        cached_value: Option<Box<RowValue<'a>>>,
+        /// Whether this formula is an array formula (CSE / dynamic array)
+        is_array: bool,
+        /// Optional referenced range for array formulas (e.g., "A1:C3")
+        array_range: Option<Cow<'a, str>>,
+    },
+}
+
+impl RowValue<'_> {
+    /// Value of a cell whose number format shows a date or time.
+    ///
+    /// Converts numbers the same way as [`DateSystem::date_cell_value`].
+    pub(crate) fn into_date(self, system: DateSystem) -> Self {
+        match self {
+            RowValue::Int(n) if system.serial_to_ymd(n as f64).is_some() => {
+                RowValue::DateTime(n as f64)
+            },
+            RowValue::Float(f) if system.serial_to_ymd(f).is_some() => RowValue::DateTime(f),
+            RowValue::Formula {
+                formula,
+                cached_value,
+                is_array,
+                array_range,
+            } => RowValue::Formula {
+                formula,
+                cached_value: cached_value.map(|value| Box::new(value.into_date(system))),
+                is_array,
+                array_range,
+            },
+            other => other,
+        }
+    }
+    /// Get the value as a string slice if it's a String variant.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            RowValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Convert into an owned [`CellValue`], copying any borrowed text.
+    pub fn into_owned(self) -> CellValue {
+        match self {
+            RowValue::Empty => CellValue::Empty,
+            RowValue::Bool(b) => CellValue::Bool(b),
+            RowValue::Int(i) => CellValue::Int(i),
+            RowValue::Float(f) => CellValue::Float(f),
+            RowValue::String(s) => CellValue::String(s.into_owned()),
+            RowValue::DateTime(d) => CellValue::DateTime(d),
+            RowValue::Error(e) => CellValue::Error(e.into_owned()),
+            RowValue::Formula {
+                formula,
+                cached_value,
+                is_array,
+                array_range,
+            } => CellValue::Formula {
+                formula: formula.into_owned(),
+                cached_value: cached_value.map(|value| Box::new(value.into_owned())),
+                is_array,
+                array_range: array_range.map(Cow::into_owned),
+            },
+        }
+    }
+}
+
+/// A single worksheet row read on demand.
+///
+/// Only non-empty cells are stored, in document order. Row and column
+/// indices follow the convention of the format's eager API: 1-based for
+/// XLSX, 0-based for XLSB.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row<'a> {
+    index: u32,
+    cells: Vec<(u32, RowValue<'a>)>,
+}
+
+impl<'a> Row<'a> {
+    /// Create a row from its index and sparse cells.
+    pub fn new(index: u32, cells: Vec<(u32, RowValue<'a>)>) -> Self {
+        Self { index, cells }
+    }
+
+    /// Row index.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    /// Sparse `(column, value)` pairs in column order.
+    pub fn cells(&self) -> &[(u32, RowValue<'a>)] {
+        &self.cells
+    }
+
+    /// Get the value in `column`, if the cell is present.
+    pub fn get(&self, column: u32) -> Option<&RowValue<'a>> {
+        self.cells
+            .iter()
+            .find(|(col, _)| *col == column)
+            .map(|(_, value)| value)
+    }
+
+    /// Number of non-empty cells in the row.
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether the row has no non-empty cells.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Convert into owned `(column, CellValue)` pairs.
+    pub fn into_cell_values(self) -> Vec<(u32, CellValue)> {
+        self.cells
+            .into_iter()
+            .map(|(col, value)| (col, value.into_owned()))
+            .collect()
+    }
+}
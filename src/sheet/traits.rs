@@ -84,6 +84,14 @@ pub trait Worksheet: Send + Sync {
     /// Returns a Cow to allow zero-copy when possible while supporting
     /// implementations that need to compute values (e.g., shared string resolution).
     fn cell_value(&self, row: u32, column: u32) -> Result<Cow<'_, CellValue>>;
+
+    /// Check if a cell's number format displays a date or time (1-based indexing).
+    ///
+    /// Dates are often stored as plain numeric serials, so this is the only
+    /// way to tell them apart. Formats without cell styles return `false`.
+    fn is_date_formatted(&self, _row: u32, _column: u32) -> bool {
+        false
+    }
 }
 
 /// Iterator over worksheets in a workbook.
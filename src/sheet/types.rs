@@ -1,10 +1,13 @@
 //! Common types for spreadsheet operations.
 
+use super::DateSystem;
+
 /// Error type for spreadsheet operations.
 pub type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Send + Sync>>;
 
 /// Types of data that can be stored in a cell.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CellValue {
     /// Empty cell
     Empty,
@@ -53,6 +56,20 @@ impl CellValue {
         }
     }
 
+    /// Get the date and time of a DateTime variant.
+    ///
+    /// The serial number is read in the workbook's date system: days since
+    /// 1900-01-00 (with Excel's fictitious 1900-02-29 as serial 60) or,
+    /// when `is_1904` is set, days since 1904-01-01. Times are rounded to
+    /// the millisecond. Returns `None` for negative serials and serial 60
+    /// of the 1900 system, which have no calendar date.
+    pub fn as_datetime(&self, is_1904: bool) -> Option<chrono::NaiveDateTime> {
+        let CellValue::DateTime(serial) = *self else {
+            return None;
+        };
+        DateSystem::from_1904_flag(is_1904).serial_to_datetime(serial)
+    }
+
     /// Infer cell value type from string representation.
     ///
     /// This function attempts to parse the string in order:
@@ -208,6 +225,36 @@ mod tests {
         assert_eq!(string_val.as_float(), None);
     }
 
+    #[test]
+    fn test_as_datetime() {
+        use chrono::NaiveDate;
+
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        assert_eq!(
+            CellValue::DateTime(44561.5).as_datetime(false),
+            date(2021, 12, 31).and_hms_opt(12, 0, 0)
+        );
+        assert_eq!(
+            CellValue::DateTime(1.0).as_datetime(false),
+            date(1900, 1, 1).and_hms_opt(0, 0, 0)
+        );
+        assert_eq!(
+            CellValue::DateTime(61.0).as_datetime(false),
+            date(1900, 3, 1).and_hms_opt(0, 0, 0)
+        );
+        assert_eq!(CellValue::DateTime(60.0).as_datetime(false), None);
+
+        // The same serial is 1462 days later in the 1904 date system
+        assert_eq!(
+            CellValue::DateTime(43099.25).as_datetime(true),
+            date(2021, 12, 31).and_hms_opt(6, 0, 0)
+        );
+
+        assert_eq!(CellValue::DateTime(-1.0).as_datetime(false), None);
+        assert_eq!(CellValue::Float(44561.5).as_datetime(false), None);
+    }
+
     #[test]
     fn test_infer_from_str_empty() {
         assert!(matches!(CellValue::infer_from_str(""), CellValue::Empty));
@@ -2,7 +2,7 @@
 
 use super::types::Result;
 use super::workbook_types::WorkbookImpl;
-use crate::common::{Error, Metadata};
+use crate::common::{Error, Metadata, Protection};
 #[allow(unused_imports)] // Used by sheet implementations
 use crate::sheet::WorkbookTrait;
 use std::path::Path;
@@ -91,11 +91,100 @@ impl Workbook {
     /// - Ideal for network data, streams, or in-memory content
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Self> {
         // Use smart detection to parse only once
-        use crate::common::detection::{DetectedFormat, detect_format_smart};
+        use crate::common::detection::detected::detect_format_checked;
 
-        let detected = detect_format_smart(bytes).ok_or_else(|| {
-            Box::new(Error::NotOfficeFile) as Box<dyn std::error::Error + Send + Sync>
-        })?;
+        let detected = detect_format_checked(bytes)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+        Self::from_detected(detected)
+    }
+
+    /// Open a password-protected workbook.
+    ///
+    /// Encrypted .xlsx and .xlsb files (Standard 2007 or Agile encryption) are
+    /// decrypted in memory. Files that are not encrypted open normally and the
+    /// password is ignored.
+    ///
+    /// # Errors
+    ///
+    /// The boxed error is a [`crate::common::Error`]:
+    /// - `IncorrectPassword` if the password does not unlock the file
+    /// - `UnsupportedEncryption` if the encryption scheme is not supported
+    /// - `Encrypted` for password-protected legacy .xls files
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::sheet::Workbook;
+    ///
+    /// let workbook = Workbook::open_with_password("protected.xlsx", "secret")?;
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    #[cfg(feature = "ooxml_encryption")]
+    pub fn open_with_password<P: AsRef<Path>>(path: P, password: &str) -> Result<Self> {
+        let bytes = std::fs::read(path.as_ref())
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+        Self::from_bytes_with_password(bytes, password)
+    }
+
+    /// Create a workbook from the bytes of a possibly password-protected file.
+    ///
+    /// See [`Workbook::open_with_password`] for details.
+    #[cfg(feature = "ooxml_encryption")]
+    pub fn from_bytes_with_password(bytes: Vec<u8>, password: &str) -> Result<Self> {
+        use crate::common::detection::detect_format_with_password;
+
+        let detected = detect_format_with_password(bytes, password)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+        Self::from_detected(detected)
+    }
+
+    /// Open an existing .xlsx file for targeted cell edits.
+    ///
+    /// Unlike [`Workbook::open`], the returned editor rewrites only the parts
+    /// touched by edits when saved, so pivot tables, charts and other content
+    /// survive unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedFeature` for formats other than .xlsx.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::sheet::Workbook;
+    ///
+    /// let mut editor = Workbook::open_for_edit("report.xlsx")?;
+    /// editor.set_cell("Sheet1", 1, 1, "Updated")?;
+    /// editor.save("report.xlsx")?;
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    #[cfg(feature = "ooxml")]
+    pub fn open_for_edit<P: AsRef<Path>>(path: P) -> Result<crate::ooxml::xlsx::WorkbookEditor> {
+        use crate::common::detection::{FileFormat, detect_file_format_from_bytes};
+
+        let bytes = std::fs::read(path.as_ref())
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
+        if detect_file_format_from_bytes(&bytes) != Some(FileFormat::Xlsx) {
+            return Err(Box::new(Error::UnsupportedFeature(
+                "only .xlsx workbooks can be edited in place",
+            )));
+        }
+        crate::ooxml::xlsx::WorkbookEditor::from_bytes(bytes)
+    }
+
+    /// Create a workbook from an already detected format.
+    ///
+    /// Shared by [`Workbook::from_bytes`] and [`crate::open_from_bytes`] so the
+    /// file is only parsed once.
+    #[allow(unused_mut)] // Only mutated for OLE encryption checks
+    pub(crate) fn from_detected(
+        mut detected: crate::common::detection::DetectedFormat,
+    ) -> Result<Self> {
+        use crate::common::detection::DetectedFormat;
+
+        #[cfg(feature = "ole")]
+        crate::common::detection::check_legacy_encryption(&mut detected)
+            .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?;
 
         // Open with appropriate implementation and extract metadata
         let (inner, metadata) = match detected {
@@ -156,6 +245,13 @@ impl Workbook {
                 (WorkbookImpl::Ods(std::cell::RefCell::new(ods)), metadata)
             },
 
+            #[cfg(feature = "ole")]
+            DetectedFormat::Encrypted(_) => {
+                return Err(Box::new(Error::Encrypted(
+                    "password-protected OOXML package".to_string(),
+                )));
+            },
+
             // Handle mismatched formats
             #[allow(unreachable_patterns)]
             _ => {
@@ -217,8 +313,8 @@ impl Workbook {
             },
 
             #[cfg(any(feature = "ole", feature = "ooxml"))]
-            WorkbookImpl::Other => Err(Box::new(Error::ParseError(
-                "Unsupported workbook type in this build".to_string(),
+            WorkbookImpl::Other => Err(Box::new(Error::UnsupportedFeature(
+                "this workbook type is not supported in this build",
             )) as Box<dyn std::error::Error + Send + Sync>),
         }
     }
@@ -260,8 +356,71 @@ impl Workbook {
                 Ok(count)
             },
             #[cfg(any(feature = "ole", feature = "ooxml"))]
-            WorkbookImpl::Other => Err(Box::new(Error::ParseError(
-                "Unsupported workbook type in this build".to_string(),
+            WorkbookImpl::Other => Err(Box::new(Error::UnsupportedFeature(
+                "this workbook type is not supported in this build",
+            )) as Box<dyn std::error::Error + Send + Sync>),
+        }
+    }
+
+    /// Check whether the workbook uses the 1904 date system.
+    ///
+    /// Date serials in [`CellValue::DateTime`](crate::sheet::CellValue::DateTime)
+    /// cells are relative to this date system; pass the result to
+    /// [`CellValue::as_datetime`](crate::sheet::CellValue::as_datetime).
+    pub fn is_1904_date_system(&self) -> bool {
+        match &self.inner {
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsx(xlsx) => xlsx.is_1904_date_system(),
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsb(xlsb) => xlsb.is_1904_date_system(),
+            #[cfg(feature = "ole")]
+            WorkbookImpl::XlsFile(xls) => xls.is_1904_date_system(),
+            #[cfg(feature = "ole")]
+            WorkbookImpl::XlsMem(xls) => xls.is_1904_date_system(),
+            #[cfg(feature = "odf")]
+            WorkbookImpl::Ods(ods_ref) => ods_ref.borrow().is_1904_date_system(),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Access the workbook through the low-level [`WorkbookTrait`] API.
+    ///
+    /// This gives cell-level access to worksheets, and can back a
+    /// `FormulaEvaluator`. Apple Numbers workbooks do not implement the
+    /// trait and return an error.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::sheet::Workbook;
+    ///
+    /// let workbook = Workbook::open("data.xlsx")?;
+    /// let value = workbook.with_workbook_trait(|wb| {
+    ///     let sheet = wb.worksheet_by_index(0)?;
+    ///     Ok(sheet.cell_value(1, 1)?.into_owned())
+    /// })?;
+    /// println!("A1 = {:?}", value);
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn with_workbook_trait<R>(
+        &self,
+        f: impl FnOnce(&dyn WorkbookTrait) -> Result<R>,
+    ) -> Result<R> {
+        match &self.inner {
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsx(xlsx) => f(xlsx),
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsb(xlsb) => f(xlsb),
+            #[cfg(feature = "ole")]
+            WorkbookImpl::XlsFile(xls) => f(xls),
+            #[cfg(feature = "ole")]
+            WorkbookImpl::XlsMem(xls) => f(xls),
+            #[cfg(feature = "odf")]
+            WorkbookImpl::Ods(ods_ref) => f(&*ods_ref.borrow()),
+            #[allow(unreachable_patterns)]
+            _ => Err(Box::new(Error::UnsupportedFeature(
+                "cell access is not supported for this workbook type",
             )) as Box<dyn std::error::Error + Send + Sync>),
         }
     }
@@ -520,8 +679,8 @@ impl Workbook {
             },
 
             #[cfg(any(feature = "ole", feature = "ooxml"))]
-            WorkbookImpl::Other => Err(Box::new(Error::ParseError(
-                "Unsupported workbook type in this build".to_string(),
+            WorkbookImpl::Other => Err(Box::new(Error::UnsupportedFeature(
+                "this workbook type is not supported in this build",
             )) as Box<dyn std::error::Error + Send + Sync>),
         }
     }
@@ -546,6 +705,130 @@ impl Workbook {
         Ok(self.cached_metadata.clone())
     }
 
+    /// Get the editing protection of the workbook.
+    ///
+    /// Reports the read-only recommendation and the "final" mark of the
+    /// document properties, the password to modify, and the protection of
+    /// the workbook structure and of the worksheets. A locked structure or
+    /// worksheet makes the workbook
+    /// [`ReadOnly`](crate::common::ProtectionKind::ReadOnly). Numbers files
+    /// are never protected.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::sheet::Workbook;
+    ///
+    /// let workbook = Workbook::open("data.xlsx")?;
+    /// let protection = workbook.protection()?;
+    /// if protection.is_protected() {
+    ///     println!("{:?}, password: {}", protection.kind, protection.has_password);
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn protection(&self) -> Result<Protection> {
+        let format = match &self.inner {
+            #[cfg(feature = "iwa")]
+            WorkbookImpl::Numbers(_) => Protection::default(),
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsx(xlsx) => xlsx.protection()?,
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsb(xlsb) => xlsb
+                .protection()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+            #[cfg(feature = "ole")]
+            WorkbookImpl::XlsFile(xls) => xls.protection(),
+            #[cfg(feature = "ole")]
+            WorkbookImpl::XlsMem(xls) => xls.protection(),
+            #[cfg(feature = "odf")]
+            WorkbookImpl::Ods(ods_ref) => ods_ref
+                .borrow()
+                .protection()
+                .map_err(|e| Box::new(e) as Box<dyn std::error::Error + Send + Sync>)?,
+            #[cfg(any(feature = "ole", feature = "ooxml"))]
+            WorkbookImpl::Other => Protection::default(),
+        };
+        Ok(Protection::from_metadata(&self.cached_metadata).or(format))
+    }
+
+    /// Check whether the workbook carries a VBA project (macros).
+    ///
+    /// This is true for .xls files with a `_VBA_PROJECT_CUR` storage and for
+    /// macro-enabled .xlsm and .xlsb files with a `vbaProject.bin` part.
+    /// Other formats have no VBA macros.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::sheet::Workbook;
+    ///
+    /// let workbook = Workbook::open("data.xlsm")?;
+    /// if workbook.has_macros() {
+    ///     println!("workbook contains macros");
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    pub fn has_macros(&self) -> bool {
+        match &self.inner {
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsx(xlsx) => xlsx.package().vba_project_part().is_some(),
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsb(xlsb) => xlsb.package().vba_project_part().is_some(),
+            #[cfg(feature = "ole")]
+            WorkbookImpl::XlsFile(xls) => xls.vba_project_data().is_some(),
+            #[cfg(feature = "ole")]
+            WorkbookImpl::XlsMem(xls) => xls.vba_project_data().is_some(),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Get the VBA project of the workbook, with the source code of its
+    /// modules.
+    ///
+    /// Returns `None` when the workbook has no macros (see
+    /// [`Self::has_macros`]).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use litchi::sheet::Workbook;
+    ///
+    /// let workbook = Workbook::open("data.xlsm")?;
+    /// if let Some(project) = workbook.vba_project()? {
+    ///     for module in project.modules() {
+    ///         println!("{}: {:?}", module.name(), module.kind());
+    ///     }
+    /// }
+    /// # Ok::<(), Box<dyn std::error::Error + Send + Sync>>(())
+    /// ```
+    #[cfg(feature = "ole")]
+    pub fn vba_project(&self) -> Result<Option<crate::ole::vba::VbaProject>> {
+        let data = match &self.inner {
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsx(xlsx) => xlsx
+                .package()
+                .vba_project_part()
+                .map(|p| p.try_blob())
+                .transpose()?,
+            #[cfg(feature = "ooxml")]
+            WorkbookImpl::Xlsb(xlsb) => xlsb
+                .package()
+                .vba_project_part()
+                .map(|p| p.try_blob())
+                .transpose()?,
+            WorkbookImpl::XlsFile(xls) => xls.vba_project_data(),
+            WorkbookImpl::XlsMem(xls) => xls.vba_project_data(),
+            #[allow(unreachable_patterns)]
+            _ => None,
+        };
+        data.map(|data| {
+            crate::ole::vba::VbaProject::parse(data)
+                .map_err(|e| Box::new(Error::from(e)) as Box<dyn std::error::Error + Send + Sync>)
+        })
+        .transpose()
+    }
+
     /// Extract metadata from a Numbers document.
     ///
     /// This extracts metadata from the Numbers bundle, similar to how
@@ -830,6 +1113,34 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_workbook_cell_access_xlsx() {
+        let path = test_data_path().join("ooxml/xlsx/DateFormatTests.xlsx");
+        let workbook = Workbook::open(&path).expect("Failed to open XLSX");
+        assert!(workbook.is_1904_date_system());
+
+        let (header, date) = workbook
+            .with_workbook_trait(|wb| {
+                let sheet = wb.worksheet_by_index(0)?;
+                assert!(!sheet.is_date_formatted(2, 2));
+                assert!(sheet.is_date_formatted(2, 3));
+                Ok((
+                    sheet.cell_value(1, 1)?.into_owned(),
+                    sheet.cell_value(2, 3)?.into_owned(),
+                ))
+            })
+            .unwrap();
+        assert_eq!(
+            header,
+            crate::sheet::CellValue::String("Result (expected)".into())
+        );
+
+        // The cached TEXT() result in A2 reads "11-10-52"
+        let datetime = date.as_datetime(workbook.is_1904_date_system()).unwrap();
+        assert_eq!(datetime.to_string(), "1952-10-11 14:35:27");
+    }
+
     #[test]
     #[cfg(all(feature = "ole", feature = "ooxml"))]
     fn test_workbook_formulas_xls() {
@@ -854,4 +1165,100 @@ mod tests {
             assert!(!names.is_empty(), "Expected worksheets with hyperlinks");
         }
     }
+
+    #[test]
+    #[cfg(all(feature = "ole", feature = "ooxml"))]
+    fn test_workbook_vba_project_xls() {
+        let path = test_data_path().join("ole/xls/SimpleMacro.xls");
+        let workbook = Workbook::open(&path).expect("Failed to open macro XLS");
+        assert!(workbook.has_macros());
+
+        let project = workbook
+            .vba_project()
+            .unwrap()
+            .expect("Expected a VBA project");
+        assert_eq!(project.name(), "VBAProject");
+        assert_eq!(project.modules().len(), 5);
+        let module = project.module("Module1").expect("Expected Module1");
+        assert!(module.source().contains("Sub TestMacro()"));
+        assert!(module.source().contains("Range(\"A2\").Select"));
+        assert_eq!(project.references()[0].name(), "stdole");
+
+        let other =
+            Workbook::open(test_data_path().join("ooxml/xlsx/DateFormatTests.xlsx")).unwrap();
+        assert!(!other.has_macros());
+        assert!(other.vba_project().unwrap().is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "ooxml")]
+    fn test_open_for_edit() {
+        let mut editor =
+            Workbook::open_for_edit(test_data_path().join("ooxml/xlsx/ExcelPivotTableSample.xlsx"))
+                .unwrap();
+        editor.set_cell("Tabelle2", 1, 1, 1.25).unwrap();
+        let workbook = Workbook::from_bytes(editor.to_bytes().unwrap()).unwrap();
+        assert_eq!(workbook.worksheet_count().unwrap(), 3);
+
+        assert!(
+            Workbook::open_for_edit(test_data_path().join("ooxml/docx/FancyFoot.docx")).is_err()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "ooxml", feature = "ole"))]
+    fn test_workbook_protection() {
+        use crate::common::{Protection, ProtectionKind};
+
+        let protection = |name: &str| {
+            Workbook::open(test_data_path().join(name))
+                .unwrap()
+                .protection()
+                .unwrap()
+        };
+        assert_eq!(
+            protection("ooxml/xlsx/sheetProtection_not_protected.xlsx"),
+            Protection::default()
+        );
+        assert_eq!(
+            protection("ooxml/xlsx/sheetProtection_allLocked.xlsx"),
+            Protection::new(ProtectionKind::ReadOnly)
+        );
+
+        // Locked structure with a password (.xls)
+        let mut writer = crate::ole::xls::XlsWriter::new();
+        writer.add_worksheet("Sheet1").unwrap();
+        writer.protect_workbook(Some("secret"), true, false);
+        let mut bytes = std::io::Cursor::new(Vec::new());
+        writer.write_to(&mut bytes).unwrap();
+        let workbook = Workbook::from_bytes(bytes.into_inner()).unwrap();
+        assert_eq!(
+            workbook.protection().unwrap(),
+            Protection::with_password(ProtectionKind::ReadOnly)
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "odf")]
+    fn test_workbook_protection_ods() {
+        use crate::common::{Protection, ProtectionKind};
+
+        let build = |protect: bool| {
+            let mut builder = crate::odf::SpreadsheetBuilder::new();
+            builder.add_sheet("Data").unwrap();
+            builder.add_row_with_numbers(&[1.0]).unwrap();
+            if protect {
+                builder.protect_sheet("Data", "secret").unwrap();
+            }
+            builder.build().unwrap()
+        };
+        let workbook = Workbook::from_bytes(build(false)).unwrap();
+        assert_eq!(workbook.protection().unwrap(), Protection::default());
+
+        let workbook = Workbook::from_bytes(build(true)).unwrap();
+        assert_eq!(
+            workbook.protection().unwrap(),
+            Protection::with_password(ProtectionKind::ReadOnly)
+        );
+    }
 }
@@ -1,9 +1,11 @@
 use proc_macro::{TokenStream, TokenTree};
 use quick_xml::Reader;
 use quick_xml::events::{BytesStart, Event};
-use quote::quote;
+use quote::{format_ident, quote};
 use std::fs;
 use std::path::Path;
+use syn::parse::{Parse, ParseStream};
+use syn::{Expr, Ident, LitStr, Token};
 
 /// Minifies an XML string literal at compile time
 ///
@@ -117,13 +119,20 @@ pub fn minified_xml(input: TokenStream) -> TokenStream {
 /// - Pre-allocates exact memory needed
 /// - Avoids format! macro overhead through direct string building
 ///
-/// The syntax is similar to `format!`, but the template is minified first.
+/// The syntax is the one of `format!`, but the template is minified first.
+/// Each argument is evaluated once, before any formatting.
 ///
 /// # Formatting Syntax
 ///
-/// - `{}` - Positional argument (uses `Display` trait)
+/// - `{}` - Next positional argument
 /// - `{0}`, `{1}`, ... - Indexed positional argument
-/// - `{name}` - Named argument
+/// - `{name}` - Named argument, or a variable in scope when no argument has that name
+/// - `{:spec}`, `{0:spec}`, `{name:spec}` - Any `format!` spec, such as `{:.2}`,
+///   `{:x}`, `{:>8}` or `{:width$}`
+/// - `{{` and `}}` - Literal braces
+///
+/// Malformed placeholders, missing arguments and unused arguments are
+/// compile errors, as they are for `format!`.
 ///
 /// # Examples
 ///
@@ -140,30 +149,78 @@ pub fn minified_xml(input: TokenStream) -> TokenStream {
 /// "#, version, name);
 /// // Result: <?xml version="1.0"?><root><name>document</name></root>
 ///
-/// // With named arguments
+/// // With named arguments and format specs
 /// let xml = minified_xml_format!(
-///     r#"<person><name>{name}</name><age>{age}</age></person>"#,
+///     r#"<person><name>{name}</name><height>{height:.2}</height><id>{id:#06x}</id></person>"#,
 ///     name = "Alice",
-///     age = 30
+///     height = 1.7,
+///     id = 42,
 /// );
-/// // Result: <person><name>Alice</name><age>30</age></person>
+/// // Result: <person><name>Alice</name><height>1.70</height><id>0x002a</id></person>
 /// ```
 #[proc_macro]
 pub fn minified_xml_format(input: TokenStream) -> TokenStream {
-    // Parse the input tokens
-    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let input = syn::parse_macro_input!(input as FormatInput);
+    expand_format(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
 
-    if tokens.is_empty() {
-        panic!("minified_xml_format! requires at least a format string");
-    }
+/// Arguments of [`minified_xml_format!`]: the template, then positional and
+/// named arguments.
+struct FormatInput {
+    template: LitStr,
+    positional: Vec<Expr>,
+    named: Vec<(Ident, Expr)>,
+}
 
-    // Extract the format string (first argument)
-    let format_str_literal = &tokens[0];
-    let TokenTree::Literal(lit) = format_str_literal else {
-        panic!("First argument must be a string literal");
-    };
+impl Parse for FormatInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let template: LitStr = input.parse()?;
+        let mut positional = Vec::new();
+        let mut named: Vec<(Ident, Expr)> = Vec::new();
 
-    let template = literal_to_string(lit.to_string());
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+
+            if input.peek(Ident) && input.peek2(Token![=]) && !input.peek2(Token![==]) {
+                let name: Ident = input.parse()?;
+                input.parse::<Token![=]>()?;
+                let expr: Expr = input.parse()?;
+                if named.iter().any(|(other, _)| *other == name) {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("duplicate argument named `{}`", name),
+                    ));
+                }
+                named.push((name, expr));
+            } else {
+                let expr: Expr = input.parse()?;
+                if !named.is_empty() {
+                    return Err(syn::Error::new_spanned(
+                        expr,
+                        "positional arguments cannot follow named arguments",
+                    ));
+                }
+                positional.push(expr);
+            }
+        }
+
+        Ok(Self {
+            template,
+            positional,
+            named,
+        })
+    }
+}
+
+/// Expand [`minified_xml_format!`] into code that builds the string.
+fn expand_format(input: &FormatInput) -> syn::Result<proc_macro2::TokenStream> {
+    let span = input.template.span();
+    let template = input.template.value();
 
     // Replace format placeholders with temporary markers before minification
     // This prevents the XML parser from being confused by {} characters
@@ -171,39 +228,187 @@ pub fn minified_xml_format(input: TokenStream) -> TokenStream {
 
     // Minify the XML template
     let minified = minify_xml(&template_with_markers)
-        .unwrap_or_else(|e| panic!("Failed to minify XML template: {}", e));
+        .map_err(|e| syn::Error::new(span, format!("Failed to minify XML template: {}", e)))?;
 
     // Restore the placeholders
     let minified_with_placeholders = restore_placeholders_from_markers(&minified, &placeholder_map);
 
-    // Parse the remaining arguments
-    let args = if tokens.len() > 1 {
-        // Skip the first token (format string) and the comma
-        let mut arg_tokens = Vec::new();
-        let mut i = 1;
+    // Parse the minified template to find format placeholders and static parts
+    let parts =
+        parse_format_string(&minified_with_placeholders).map_err(|e| syn::Error::new(span, e))?;
+
+    // Evaluate every argument once, by reference, as `format!` does
+    let positional_idents: Vec<Ident> = (0..input.positional.len())
+        .map(|idx| format_ident!("__arg{}", idx))
+        .collect();
+    let named_idents: Vec<Ident> = input
+        .named
+        .iter()
+        .map(|(name, _)| format_ident!("__arg_{}", name))
+        .collect();
+    let positional_exprs = &input.positional;
+    let named_exprs = input.named.iter().map(|(_, expr)| expr);
+
+    let mut used_positional = vec![false; input.positional.len()];
+    let mut used_named = vec![false; input.named.len()];
+    let mut resolve = |arg: &PlaceholderType, next: &mut usize| -> syn::Result<ArgRef> {
+        match arg {
+            PlaceholderType::NextPositional | PlaceholderType::Positional(_) => {
+                let idx = match arg {
+                    PlaceholderType::Positional(idx) => *idx,
+                    _ => {
+                        *next += 1;
+                        *next - 1
+                    },
+                };
+                let Some(ident) = positional_idents.get(idx) else {
+                    return Err(syn::Error::new(
+                        span,
+                        format!(
+                            "invalid reference to positional argument {} ({} given)",
+                            idx,
+                            positional_idents.len()
+                        ),
+                    ));
+                };
+                used_positional[idx] = true;
+                Ok(ArgRef::Bound(ident.clone()))
+            },
+            PlaceholderType::Named(name) => {
+                match input.named.iter().position(|(other, _)| other == name) {
+                    Some(idx) => {
+                        used_named[idx] = true;
+                        Ok(ArgRef::Bound(named_idents[idx].clone()))
+                    },
+                    // Capture a variable in scope, as `format!` does
+                    None => Ok(ArgRef::Captured(Ident::new(name, span))),
+                }
+            },
+        }
+    };
 
-        // Skip comma after format string
-        if let Some(TokenTree::Punct(p)) = tokens.get(i)
-            && p.as_char() == ','
-        {
-            i += 1;
+    // Calculate static size
+    let static_size: usize = parts
+        .iter()
+        .filter_map(|p| match p {
+            FormatPart::Static(s) => Some(s.len()),
+            _ => None,
+        })
+        .sum();
+
+    let mut statements = Vec::with_capacity(parts.len());
+    let mut next_positional = 0usize;
+    for part in &parts {
+        match part {
+            FormatPart::Static(text) => {
+                statements.push(quote! { __result.push_str(#text); });
+            },
+            FormatPart::Placeholder(arg, spec) => {
+                let value = resolve(arg, &mut next_positional)?;
+
+                // Width and precision arguments (`width$`) become named
+                // arguments of the `write!` call
+                let mut spec_code = String::with_capacity(spec.len());
+                let mut counts = Vec::new();
+                let mut rest = spec.as_str();
+                while let Some((name, before, after)) = next_count_argument(rest) {
+                    let count_arg = if name.chars().all(|c| c.is_ascii_digit()) {
+                        PlaceholderType::Positional(name.parse().map_err(|_| {
+                            syn::Error::new(span, format!("invalid argument index `{}`", name))
+                        })?)
+                    } else {
+                        PlaceholderType::Named(name.to_string())
+                    };
+                    let count_ident = format_ident!("__count{}", counts.len());
+                    spec_code.push_str(before);
+                    spec_code.push_str(&count_ident.to_string());
+                    spec_code.push('$');
+                    counts.push((count_ident, resolve(&count_arg, &mut 0)?.count()));
+                    rest = after;
+                }
+                spec_code.push_str(rest);
+
+                let format = LitStr::new(&format!("{{:{}}}", spec_code), span);
+                let value = value.value();
+                let count_names = counts.iter().map(|(name, _)| name);
+                let count_values = counts.iter().map(|(_, value)| value);
+                statements.push(quote! {
+                    let _ = ::std::fmt::Write::write_fmt(
+                        &mut __result,
+                        ::std::format_args!(#format, #value #(, #count_names = #count_values)*),
+                    );
+                });
+            },
         }
+    }
+
+    if let Some(idx) = used_positional.iter().position(|used| !used) {
+        return Err(syn::Error::new_spanned(
+            &input.positional[idx],
+            "argument never used",
+        ));
+    }
+    if let Some(idx) = used_named.iter().position(|used| !used) {
+        return Err(syn::Error::new_spanned(
+            &input.named[idx].1,
+            "named argument never used",
+        ));
+    }
 
-        while i < tokens.len() {
-            arg_tokens.push(tokens[i].clone());
-            i += 1;
+    Ok(quote! {
+        {
+            #(let #positional_idents = &(#positional_exprs);)*
+            #(let #named_idents = &(#named_exprs);)*
+            let mut __result = ::std::string::String::with_capacity(#static_size + 32);
+            #(#statements)*
+            __result
         }
+    })
+}
 
-        TokenStream::from_iter(arg_tokens)
-    } else {
-        TokenStream::new()
-    };
+/// An argument a placeholder refers to
+enum ArgRef {
+    /// An argument of the macro, bound to a reference
+    Bound(Ident),
+    /// A variable in scope, named by an implicit named argument
+    Captured(Ident),
+}
 
-    // Parse the minified template to find format placeholders and static parts
-    let parts = parse_format_string(&minified_with_placeholders);
+impl ArgRef {
+    /// Expression for a formatted value
+    fn value(&self) -> proc_macro2::TokenStream {
+        match self {
+            ArgRef::Bound(ident) => quote! { #ident },
+            ArgRef::Captured(ident) => quote! { &#ident },
+        }
+    }
 
-    // Generate optimized code
-    generate_format_code(&parts, args)
+    /// Expression for a width or precision, which must be a `usize`
+    fn count(&self) -> proc_macro2::TokenStream {
+        match self {
+            ArgRef::Bound(ident) => quote! { *#ident },
+            ArgRef::Captured(ident) => quote! { #ident },
+        }
+    }
+}
+
+/// Find the next width or precision argument (`name$` or `0$`) in a format spec.
+///
+/// Returns the argument name with the spec text before and after `name$`.
+fn next_count_argument(spec: &str) -> Option<(&str, &str, &str)> {
+    let mut search = 0;
+    while let Some(offset) = spec[search..].find('$') {
+        let dollar = search + offset;
+        let start = spec[..dollar]
+            .rfind(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .map_or(0, |pos| pos + 1);
+        // A `$` with no name before it is a fill character, as in `{:$>8}`
+        if start < dollar {
+            return Some((&spec[start..dollar], &spec[..start], &spec[dollar + 1..]));
+        }
+        search = dollar + 1;
+    }
+    None
 }
 
 /// Replace format placeholders with unique markers that won't confuse the XML parser
@@ -279,8 +484,8 @@ fn restore_placeholders_from_markers(minified: &str, placeholders: &[String]) ->
 enum FormatPart {
     /// Static text that doesn't need formatting
     Static(String),
-    /// A format placeholder (either positional index or named argument)
-    Placeholder(PlaceholderType),
+    /// A format placeholder and its format spec (the text after `:`)
+    Placeholder(PlaceholderType, String),
 }
 
 /// Type of format placeholder
@@ -295,7 +500,7 @@ enum PlaceholderType {
 }
 
 /// Parse a format string into static parts and placeholders
-fn parse_format_string(template: &str) -> Vec<FormatPart> {
+fn parse_format_string(template: &str) -> Result<Vec<FormatPart>, String> {
     let mut parts = Vec::new();
     let mut current_static = String::new();
     let mut chars = template.chars().peekable();
@@ -321,31 +526,47 @@ fn parse_format_string(template: &str) -> Vec<FormatPart> {
                 match chars.next() {
                     Some('}') => break,
                     Some(ch) => placeholder_content.push(ch),
-                    None => panic!("Unclosed format placeholder in template"),
+                    None => return Err("unclosed format placeholder in template".to_string()),
                 }
             }
 
-            // Determine placeholder type
-            let placeholder = if placeholder_content.is_empty() {
+            // Split off the format spec and determine placeholder type
+            let (argument, spec) = placeholder_content
+                .split_once(':')
+                .unwrap_or((placeholder_content.as_str(), ""));
+            let placeholder = if argument.is_empty() {
                 PlaceholderType::NextPositional
-            } else if placeholder_content.chars().all(|c| c.is_ascii_digit()) {
+            } else if argument.chars().all(|c| c.is_ascii_digit()) {
                 PlaceholderType::Positional(
-                    placeholder_content
+                    argument
                         .parse()
-                        .expect("Invalid positional index"),
+                        .map_err(|_| format!("invalid positional index `{}`", argument))?,
                 )
+            } else if is_identifier(argument) {
+                PlaceholderType::Named(argument.to_string())
             } else {
-                PlaceholderType::Named(placeholder_content)
+                return Err(format!(
+                    "invalid format placeholder `{{{}}}`",
+                    placeholder_content
+                ));
             };
+            if spec.contains(".*") {
+                return Err(format!(
+                    "precision `.*` is not supported in `{{{}}}`; use `.N$` or `.name$`",
+                    placeholder_content
+                ));
+            }
 
-            parts.push(FormatPart::Placeholder(placeholder));
+            parts.push(FormatPart::Placeholder(placeholder, spec.to_string()));
         } else if ch == '}' {
             // Check for escaped brace }}
             if chars.peek() == Some(&'}') {
                 chars.next();
                 current_static.push('}');
             } else {
-                panic!("Unmatched }} in format string");
+                return Err(
+                    "unmatched `}` in format string; use `}}` for a literal brace".to_string(),
+                );
             }
         } else {
             current_static.push(ch);
@@ -357,132 +578,15 @@ fn parse_format_string(template: &str) -> Vec<FormatPart> {
         parts.push(FormatPart::Static(current_static));
     }
 
-    parts
+    Ok(parts)
 }
 
-/// Generate optimized formatting code
-fn generate_format_code(parts: &[FormatPart], args: TokenStream) -> TokenStream {
-    use proc_macro::TokenTree as TT;
-
-    // Parse arguments into positional and named
-    let mut positional_args = Vec::new();
-    let mut named_args = std::collections::HashMap::new();
-
-    let arg_tokens: Vec<TT> = args.into_iter().collect();
-    let mut i = 0;
-
-    while i < arg_tokens.len() {
-        // Check if this is a named argument (ident = value)
-        if let Some(TT::Ident(name)) = arg_tokens.get(i)
-            && let Some(TT::Punct(punct)) = arg_tokens.get(i + 1)
-            && punct.as_char() == '='
-        {
-            // Named argument
-            let name_str = name.to_string();
-            let mut value_tokens = Vec::new();
-            i += 2; // Skip name and =
-
-            // Collect value tokens until comma or end
-            while i < arg_tokens.len() {
-                if let TT::Punct(p) = &arg_tokens[i]
-                    && p.as_char() == ','
-                {
-                    i += 1;
-                    break;
-                }
-                value_tokens.push(arg_tokens[i].clone());
-                i += 1;
-            }
-
-            named_args.insert(name_str, value_tokens);
-            continue;
-        }
-
-        // Positional argument
-        let mut value_tokens = Vec::new();
-        while i < arg_tokens.len() {
-            if let TT::Punct(p) = &arg_tokens[i]
-                && p.as_char() == ','
-            {
-                i += 1;
-                break;
-            }
-            value_tokens.push(arg_tokens[i].clone());
-            i += 1;
-        }
-
-        if !value_tokens.is_empty() {
-            positional_args.push(value_tokens);
-        }
-    }
-
-    // Calculate static size
-    let static_size: usize = parts
-        .iter()
-        .filter_map(|p| match p {
-            FormatPart::Static(s) => Some(s.len()),
-            _ => None,
-        })
-        .sum();
-
-    // Generate code to build the string - build it as a string to avoid ToTokens issues
-    let mut code = format!(
-        "{{ let mut __result = ::std::string::String::with_capacity({} + 32);",
-        static_size
-    );
-
-    let mut next_positional_idx = 0;
-
-    for part in parts {
-        match part {
-            FormatPart::Static(text) => {
-                code.push_str(&format!("__result.push_str({:?});", text));
-            },
-            FormatPart::Placeholder(placeholder) => {
-                let arg_tokens = match placeholder {
-                    PlaceholderType::NextPositional => {
-                        if let Some(arg) = positional_args.get(next_positional_idx) {
-                            next_positional_idx += 1;
-                            arg
-                        } else {
-                            panic!("Not enough positional arguments");
-                        }
-                    },
-                    PlaceholderType::Positional(idx) => {
-                        if let Some(arg) = positional_args.get(*idx) {
-                            arg
-                        } else {
-                            panic!("Positional argument {} not found", idx);
-                        }
-                    },
-                    PlaceholderType::Named(name) => {
-                        if let Some(arg) = named_args.get(name) {
-                            arg
-                        } else {
-                            panic!("Named argument '{}' not found", name);
-                        }
-                    },
-                };
-
-                // Convert the token trees to a string representation
-                let arg_str: String = arg_tokens
-                    .iter()
-                    .map(|tt| tt.to_string())
-                    .collect::<Vec<_>>()
-                    .join("");
-
-                code.push_str(&format!(
-                    "{{ use ::std::fmt::Write; let _ = write!(&mut __result, \"{{}}\", {}); }}",
-                    arg_str
-                ));
-            },
-        }
-    }
-
-    code.push_str("__result }");
-
-    // Parse the generated code string back into a TokenStream
-    code.parse().expect("Failed to parse generated code")
+/// Whether `name` can be a named argument
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && name != "_"
 }
 
 /// Handles the conversion between String Literals represented by TokenStream and Rust String type
@@ -531,6 +635,11 @@ fn literal_to_string(mut literal: String) -> String {
 /// - Collapses empty element tags
 /// - Handles CDATA sections properly
 ///
+/// Text is copied untouched inside elements with `xml:space="preserve"` and
+/// their descendants, up to a descendant that resets it with
+/// `xml:space="default"`. The same holds for
+/// [`SIGNIFICANT_WHITESPACE_ELEMENTS`], whose text is always significant.
+///
 /// # Performance
 /// - Zero-copy where possible using `Cow<[u8]>`
 /// - Single-pass processing
@@ -544,9 +653,22 @@ fn minify_xml(xml: &str) -> Result<String, Box<dyn std::error::Error>> {
 
     // Stack to track element names for collapsing empty tags
     let mut tag_stack: Vec<BytesStart<'static>> = Vec::new();
+    // Whether whitespace is significant in each open element
+    let mut space_stack: Vec<bool> = Vec::new();
+    // Text and entity references since the last markup, trimmed as one run
+    let mut pending_text: Vec<u8> = Vec::new();
 
     loop {
-        match reader.read_event_into(&mut buf)? {
+        let event = reader.read_event_into(&mut buf)?;
+        if !matches!(
+            event,
+            Event::Text(_) | Event::GeneralRef(_) | Event::Comment(_) | Event::PI(_)
+        ) {
+            let preserve = space_stack.last().copied().unwrap_or(false);
+            flush_text(&mut output, &mut tag_stack, &mut pending_text, preserve)?;
+        }
+
+        match event {
             Event::Eof => {
                 // Flush any remaining buffered start tags before EOF
                 // (this can happen if the root element never closes in the stream)
@@ -582,6 +704,9 @@ fn minify_xml(xml: &str) -> Result<String, Box<dyn std::error::Error>> {
 
             // Handle start tags - buffer them to check if they can be collapsed
             Event::Start(e) => {
+                let inherited = space_stack.last().copied().unwrap_or(false);
+                space_stack.push(preserves_space(&e, inherited)?);
+
                 // Clone the tag for our stack (we need owned data)
                 let owned = e.to_owned();
                 tag_stack.push(owned);
@@ -607,6 +732,7 @@ fn minify_xml(xml: &str) -> Result<String, Box<dyn std::error::Error>> {
 
             // Handle end tags - check if we can collapse with start tag
             Event::End(e) => {
+                space_stack.pop();
                 if let Some(start_tag) = tag_stack.pop() {
                     // Check if this end tag matches the last start tag
                     // If so, we can collapse to an empty tag
@@ -654,35 +780,8 @@ fn minify_xml(xml: &str) -> Result<String, Box<dyn std::error::Error>> {
                 }
             },
 
-            // Handle text content - trim whitespace intelligently
-            Event::Text(e) => {
-                // Get the text content
-                let text = e.as_ref();
-
-                // Intelligently handle whitespace
-                // Skip pure whitespace between tags, otherwise trim both leading and trailing whitespace
-                // This is safe for most XML use cases where whitespace between elements is not significant
-                let trimmed = if is_whitespace_only(text) {
-                    &[]
-                } else {
-                    trim_whitespace(text)
-                };
-
-                // Only flush buffered start tags if we have non-whitespace text content
-                if !trimmed.is_empty() {
-                    // Flush ALL buffered start tags since we have text content
-                    // Use mem::take to efficiently move all elements out of the stack
-                    let tags_to_flush = std::mem::take(&mut tag_stack);
-                    for start_tag in tags_to_flush {
-                        output.push(b'<');
-                        output.extend_from_slice(start_tag.name().as_ref());
-                        write_attributes(&mut output, &start_tag)?;
-                        output.push(b'>');
-                    }
-
-                    output.extend_from_slice(trimmed);
-                }
-            },
+            // Collect text content; it is trimmed as a whole before the next markup
+            Event::Text(e) => pending_text.extend_from_slice(e.as_ref()),
 
             // Preserve CDATA sections as-is (they may contain formatting-sensitive content)
             Event::CData(e) => {
@@ -700,9 +799,12 @@ fn minify_xml(xml: &str) -> Result<String, Box<dyn std::error::Error>> {
                 output.extend_from_slice(b"]]>");
             },
 
-            // Skip entity references - they'll be handled by the parser
-            // This case is for general entity references which are rare in modern XML
-            Event::GeneralRef(_) => continue,
+            // Keep entity and character references as part of the text
+            Event::GeneralRef(e) => {
+                pending_text.push(b'&');
+                pending_text.extend_from_slice(e.as_ref());
+                pending_text.push(b';');
+            },
         }
 
         buf.clear();
@@ -720,6 +822,66 @@ fn minify_xml(xml: &str) -> Result<String, Box<dyn std::error::Error>> {
     Ok(result)
 }
 
+/// Elements whose text is always significant, whatever their `xml:space`.
+///
+/// Their text, and that of their descendants, is copied without trimming as
+/// if they carried `xml:space="preserve"`.
+const SIGNIFICANT_WHITESPACE_ELEMENTS: &[&[u8]] = &[
+    b"w:t",
+    b"w:delText",
+    b"w:instrText",
+    b"a:t",
+    b"text:p",
+    b"text:h",
+];
+
+/// Whether whitespace is significant in the element started by `tag`.
+///
+/// `inherited` is the setting of the parent element.
+fn preserves_space(tag: &BytesStart, inherited: bool) -> Result<bool, quick_xml::Error> {
+    if SIGNIFICANT_WHITESPACE_ELEMENTS.contains(&tag.name().as_ref()) {
+        return Ok(true);
+    }
+    for attr in tag.attributes() {
+        let attr = attr?;
+        if attr.key.as_ref() == b"xml:space" {
+            return Ok(attr.value.as_ref() == b"preserve");
+        }
+    }
+    Ok(inherited)
+}
+
+/// Write the collected text run, trimmed unless whitespace is significant.
+///
+/// Buffered start tags are written first when any text remains.
+fn flush_text(
+    output: &mut Vec<u8>,
+    tag_stack: &mut Vec<BytesStart<'static>>,
+    pending_text: &mut Vec<u8>,
+    preserve: bool,
+) -> Result<(), quick_xml::Error> {
+    let text: &[u8] = if preserve {
+        pending_text
+    } else if is_whitespace_only(pending_text) {
+        &[]
+    } else {
+        trim_whitespace(pending_text)
+    };
+
+    if !text.is_empty() {
+        // Flush ALL buffered start tags since we have text content
+        for start_tag in std::mem::take(tag_stack) {
+            output.push(b'<');
+            output.extend_from_slice(start_tag.name().as_ref());
+            write_attributes(output, &start_tag)?;
+            output.push(b'>');
+        }
+        output.extend_from_slice(text);
+    }
+    pending_text.clear();
+    Ok(())
+}
+
 /// Helper function to write attributes efficiently
 #[inline]
 fn write_attributes(output: &mut Vec<u8>, tag: &BytesStart) -> Result<(), quick_xml::Error> {
@@ -950,78 +1112,245 @@ mod tests {
         assert!(!minified.contains("\n"), "Newlines should be removed");
     }
 
+    #[test]
+    fn test_preserve_space_run() {
+        let input = r#"<w:p>
+            <w:r><w:t>Hello</w:t></w:r>
+            <w:r><w:t xml:space="preserve"> </w:t></w:r>
+            <w:r><w:t>world</w:t></w:r>
+        </w:p>"#;
+        let minified = minify_xml(input).unwrap();
+        assert_eq!(
+            minified,
+            r#"<w:p><w:r><w:t>Hello</w:t></w:r><w:r><w:t xml:space="preserve"> </w:t></w:r><w:r><w:t>world</w:t></w:r></w:p>"#
+        );
+    }
+
+    #[test]
+    fn test_nested_preserve_scopes() {
+        let preserved = "<a>  x  </a><c>\n  <d> z </d>\n</c>";
+        let input = format!(
+            "<doc>\n  <root xml:space=\"preserve\">{preserved}<b xml:space=\"default\">\n  \
+             y  <e>  w  </e>\n</b></root>\n  <f>  v  </f>\n</doc>"
+        );
+        let minified = minify_xml(&input).unwrap();
+        assert_eq!(
+            minified,
+            format!(
+                "<doc><root xml:space=\"preserve\">{preserved}<b xml:space=\"default\">y<e>w</e>\
+                 </b></root><f>v</f></doc>"
+            )
+        );
+    }
+
+    #[test]
+    fn test_significant_whitespace_elements() {
+        let input = r#"
+            <p:sp>
+                <a:p>
+                    <a:r><a:t> leading and trailing </a:t></a:r>
+                </a:p>
+                <text:p text:style-name="P1">Tab <text:span>here</text:span> </text:p>
+            </p:sp>
+        "#;
+        let minified = minify_xml(input).unwrap();
+        assert_eq!(
+            minified,
+            r#"<p:sp><a:p><a:r><a:t> leading and trailing </a:t></a:r></a:p><text:p text:style-name="P1">Tab <text:span>here</text:span> </text:p></p:sp>"#
+        );
+    }
+
+    #[test]
+    fn test_preserved_regions_byte_identical() {
+        let cdata = "<![CDATA[  keep <this>\n  as is  ]]>";
+        let preserved = "<w:t xml:space=\"preserve\">\t two  spaces &amp; a tab </w:t>";
+        let input = format!("<root>\n  <code>{cdata}</code>\n  <w:r>{preserved}</w:r>\n</root>");
+        let minified = minify_xml(&input).unwrap();
+        assert_eq!(
+            minified,
+            format!("<root><code>{cdata}</code><w:r>{preserved}</w:r></root>")
+        );
+    }
+
+    #[test]
+    fn test_entity_references_kept() {
+        let input = "<root>\n  Tom &amp; Jerry &#169; 2024\n  <!-- note -->\n</root>";
+        let minified = minify_xml(input).unwrap();
+        assert_eq!(minified, "<root>Tom &amp; Jerry &#169; 2024</root>");
+    }
+
     #[test]
     fn test_parse_format_string_empty() {
-        let parts = parse_format_string("hello world");
+        let parts = parse_format_string("hello world").unwrap();
         assert_eq!(parts.len(), 1);
         assert!(matches!(parts[0], FormatPart::Static(ref s) if s == "hello world"));
     }
 
     #[test]
     fn test_parse_format_string_simple_placeholder() {
-        let parts = parse_format_string("hello {}");
+        let parts = parse_format_string("hello {}").unwrap();
         assert_eq!(parts.len(), 2);
         assert!(matches!(parts[0], FormatPart::Static(ref s) if s == "hello "));
         assert!(matches!(
             parts[1],
-            FormatPart::Placeholder(PlaceholderType::NextPositional)
+            FormatPart::Placeholder(PlaceholderType::NextPositional, _)
         ));
     }
 
     #[test]
     fn test_parse_format_string_indexed_placeholder() {
-        let parts = parse_format_string("{0} and {1}");
+        let parts = parse_format_string("{0} and {1}").unwrap();
         assert_eq!(parts.len(), 3);
         assert!(matches!(
             parts[0],
-            FormatPart::Placeholder(PlaceholderType::Positional(0))
+            FormatPart::Placeholder(PlaceholderType::Positional(0), _)
         ));
         assert!(matches!(parts[1], FormatPart::Static(ref s) if s == " and "));
         assert!(matches!(
             parts[2],
-            FormatPart::Placeholder(PlaceholderType::Positional(1))
+            FormatPart::Placeholder(PlaceholderType::Positional(1), _)
         ));
     }
 
     #[test]
     fn test_parse_format_string_named_placeholder() {
-        let parts = parse_format_string("Hello {name}!");
+        let parts = parse_format_string("Hello {name}!").unwrap();
         assert_eq!(parts.len(), 3);
         assert!(matches!(parts[0], FormatPart::Static(ref s) if s == "Hello "));
         assert!(
-            matches!(parts[1], FormatPart::Placeholder(PlaceholderType::Named(ref n)) if n == "name")
+            matches!(parts[1], FormatPart::Placeholder(PlaceholderType::Named(ref n), _) if n == "name")
         );
         assert!(matches!(parts[2], FormatPart::Static(ref s) if s == "!"));
     }
 
     #[test]
     fn test_parse_format_string_escaped_braces() {
-        let parts = parse_format_string("{{escaped}} and {} normal");
+        let parts = parse_format_string("{{escaped}} and {} normal").unwrap();
         assert_eq!(parts.len(), 3);
         assert!(matches!(parts[0], FormatPart::Static(ref s) if s == "{escaped} and "));
         assert!(matches!(
             parts[1],
-            FormatPart::Placeholder(PlaceholderType::NextPositional)
+            FormatPart::Placeholder(PlaceholderType::NextPositional, _)
         ));
         assert!(matches!(parts[2], FormatPart::Static(ref s) if s == " normal"));
     }
 
     #[test]
     fn test_parse_format_string_mixed() {
-        let parts = parse_format_string("<root><name>{}</name><age>{age}</age></root>");
+        let parts = parse_format_string("<root><name>{}</name><age>{age}</age></root>").unwrap();
         assert_eq!(parts.len(), 5);
         assert!(matches!(parts[0], FormatPart::Static(ref s) if s == "<root><name>"));
         assert!(matches!(
             parts[1],
-            FormatPart::Placeholder(PlaceholderType::NextPositional)
+            FormatPart::Placeholder(PlaceholderType::NextPositional, _)
         ));
         assert!(matches!(parts[2], FormatPart::Static(ref s) if s == "</name><age>"));
         assert!(
-            matches!(parts[3], FormatPart::Placeholder(PlaceholderType::Named(ref n)) if n == "age")
+            matches!(parts[3], FormatPart::Placeholder(PlaceholderType::Named(ref n), _) if n == "age")
         );
         assert!(matches!(parts[4], FormatPart::Static(ref s) if s == "</age></root>"));
     }
 
+    #[test]
+    fn test_parse_format_string_specs() {
+        let parts = parse_format_string("<a>{:.2}</a><b>{0:#x}</b><c>{name:>width$}</c>").unwrap();
+        assert_eq!(parts.len(), 7);
+        assert!(matches!(
+            parts[1],
+            FormatPart::Placeholder(PlaceholderType::NextPositional, ref spec) if spec == ".2"
+        ));
+        assert!(matches!(
+            parts[3],
+            FormatPart::Placeholder(PlaceholderType::Positional(0), ref spec) if spec == "#x"
+        ));
+        assert!(matches!(
+            parts[5],
+            FormatPart::Placeholder(PlaceholderType::Named(ref n), ref spec)
+                if n == "name" && spec == ">width$"
+        ));
+    }
+
+    #[test]
+    fn test_parse_format_string_errors() {
+        assert!(parse_format_string("<a>{</a>").is_err());
+        assert!(parse_format_string("<a>}</a>").is_err());
+        assert!(parse_format_string("<a>{not valid}</a>").is_err());
+        assert!(parse_format_string("<a>{:.*}</a>").is_err());
+    }
+
+    #[test]
+    fn test_next_count_argument() {
+        assert_eq!(next_count_argument(">8"), None);
+        assert_eq!(next_count_argument("$>8"), None);
+        assert_eq!(
+            next_count_argument(">width$.prec$"),
+            Some(("width", ">", ".prec$"))
+        );
+        assert_eq!(next_count_argument(".1$"), Some(("1", ".", "")));
+    }
+
+    fn expand(input: proc_macro2::TokenStream) -> syn::Result<String> {
+        let input: FormatInput = syn::parse2(input)?;
+        expand_format(&input).map(|tokens| tokens.to_string())
+    }
+
+    #[test]
+    fn test_expand_format_specs() {
+        let expanded = expand(quote! {
+            "<v>\n  {:.2}\n</v><w width=\"{w:>width$}\"/>", value.get::<f64>(), w = "x", width = 4
+        })
+        .unwrap();
+        let expected = quote! {
+            {
+                let __arg0 = &(value.get::<f64>());
+                let __arg_w = &("x");
+                let __arg_width = &(4);
+                let mut __result = ::std::string::String::with_capacity(20usize + 32);
+                __result.push_str("<v>");
+                let _ = ::std::fmt::Write::write_fmt(
+                    &mut __result,
+                    ::std::format_args!("{:.2}", __arg0),
+                );
+                __result.push_str("</v><w width=\"");
+                let _ = ::std::fmt::Write::write_fmt(
+                    &mut __result,
+                    ::std::format_args!("{:>__count0$}", __arg_w, __count0 = *__arg_width),
+                );
+                __result.push_str("\"/>");
+                __result
+            }
+        };
+        assert_eq!(expanded, expected.to_string());
+    }
+
+    #[test]
+    fn test_expand_format_captures_variables() {
+        let expanded = expand(quote! { "<n>{count:03}</n>" }).unwrap();
+        assert!(expanded.contains("format_args ! (\"{:03}\" , & count)"));
+    }
+
+    #[test]
+    fn test_expand_format_errors() {
+        let message = |input| expand(input).unwrap_err().to_string();
+        assert_eq!(
+            message(quote! { "<a>{1}</a>", x }),
+            "invalid reference to positional argument 1 (1 given)"
+        );
+        assert_eq!(message(quote! { "<a>{}</a>", x, y }), "argument never used");
+        assert_eq!(
+            message(quote! { "<a>{}</a>", x, unused = 1 }),
+            "named argument never used"
+        );
+        assert_eq!(
+            message(quote! { "<a>{}</a>", a = 1, x }),
+            "positional arguments cannot follow named arguments"
+        );
+        assert_eq!(
+            message(quote! { "<a>{a}</a>", a = 1, a = 2 }),
+            "duplicate argument named `a`"
+        );
+    }
+
     #[test]
     fn test_replace_placeholders_with_markers_simple() {
         let (result, placeholders) = replace_placeholders_with_markers("<root>{}</root>");
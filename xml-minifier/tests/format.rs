@@ -0,0 +1,68 @@
+use xml_minifier::minified_xml_format;
+
+#[test]
+fn test_format_specs() {
+    let xml = minified_xml_format!(
+        r#"
+        <values>
+            <fixed>{:.2}</fixed>
+            <hex>{1:#06x}</hex>
+            <padded>{name:>8}</padded>
+            <debug>{list:?}</debug>
+        </values>
+        "#,
+        1.23456,
+        3,
+        name = "right",
+        list = [1, 2],
+    );
+    assert_eq!(
+        xml,
+        "<values><fixed>1.23</fixed><hex>0x0003</hex><padded>   right</padded>\
+         <debug>[1, 2]</debug></values>"
+    );
+}
+
+#[test]
+fn test_format_count_arguments() {
+    let width = 6;
+    let xml = minified_xml_format!(
+        r#"<row a="{:>1$}" b="{2:.prec$}" c="{2:<width$}|"/>"#,
+        "x",
+        4,
+        2.5,
+        prec = 3,
+    );
+    assert_eq!(xml, r#"<row a="   x" b="2.500" c="2.5   |"/>"#);
+}
+
+#[test]
+fn test_format_expression_arguments() {
+    let values = [1.5f64, 2.25];
+    let xml = minified_xml_format!(
+        "<sum>{}</sum><first>{}</first><parsed>{}</parsed>",
+        values.iter().map(|v| v * 2.0).sum::<f64>(),
+        values.first().copied().unwrap_or_default(),
+        "42".parse::<u32>().unwrap(),
+    );
+    assert_eq!(xml, "<sum>7.5</sum><first>1.5</first><parsed>42</parsed>");
+}
+
+#[test]
+fn test_format_arguments_evaluated_once() {
+    let mut calls = 0;
+    let mut next = || {
+        calls += 1;
+        calls
+    };
+    let xml = minified_xml_format!("<a>{0}</a><b>{0}</b>", next());
+    assert_eq!(xml, "<a>1</a><b>1</b>");
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn test_format_captured_variables_and_braces() {
+    let id = 7;
+    let xml = minified_xml_format!("<item id=\"{id:03}\">{{literal}}</item>");
+    assert_eq!(xml, "<item id=\"007\">{literal}</item>");
+}
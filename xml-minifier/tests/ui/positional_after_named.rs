@@ -0,0 +1,5 @@
+use xml_minifier::minified_xml_format;
+
+fn main() {
+    let _ = minified_xml_format!("<a>{name}{}</a>", name = 1, 2);
+}